@@ -818,7 +818,7 @@ export default function SettingsPage() {
     async (payload: SettingsImportPayload) => {
       if ("version" in payload && payload.version === "2.0") {
         const { configImport } = await import("@/lib/tauri");
-        await configImport(payload.backendConfig);
+        const report = await configImport(payload.backendConfig);
         const importedAppSettings = normalizeImportedAppSettings(
           payload.appSettings,
         );
@@ -841,6 +841,11 @@ export default function SettingsPage() {
         setFailedSaveKeys([]);
         setFailedSaveMessages({});
         toast.success(t("settings.importSuccess"));
+        if (report.issues.length > 0) {
+          toast.warning(
+            t("settings.importValuesReset", { count: report.issues.length }),
+          );
+        }
         return;
       }
 
@@ -1639,6 +1644,16 @@ export default function SettingsPage() {
     [scrollToSection],
   );
 
+  // Open the section requested via `?section=` (e.g. from a cognia:// link)
+  useEffect(() => {
+    const requested = new URLSearchParams(window.location.search).get("section");
+    const section = SETTINGS_SECTION_IDS.find((id) => id === requested);
+    if (!section) return;
+    const targetGroup = getGroupForSection(section);
+    if (targetGroup) setActiveTab(targetGroup);
+    window.setTimeout(() => scrollToSection(section), 50);
+  }, [scrollToSection]);
+
   useSettingsShortcuts({
     onSave: handleSave,
     onReset: handleReset,
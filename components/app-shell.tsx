@@ -18,6 +18,8 @@ import { BackgroundImage } from "@/components/layout/background-image";
 import { Breadcrumb } from "@/components/layout/breadcrumb";
 import { LogDrawer } from "@/components/log/log-drawer";
 import { CrashRecoveryDialog } from "@/components/crash-recovery-dialog";
+import { DeepLinkConfirmDialog } from "@/components/deep-link-confirm-dialog";
+import { SettingsLoadReportNotice } from "@/components/settings-load-report-notice";
 import { FeedbackDialog } from "@/components/feedback";
 import { Button } from "@/components/ui/button";
 import { CommandPalette } from "@/components/command-palette";
@@ -311,6 +313,8 @@ export function AppShell({ children }: AppShellProps) {
         <CommandPalette open={commandOpen} onOpenChange={setCommandOpen} />
         <LogDrawer />
         <CrashRecoveryDialog t={t} />
+        <DeepLinkConfirmDialog t={t} />
+        <SettingsLoadReportNotice t={t} />
         <FeedbackDialog />
 
         {onboarding.isHydrated && (
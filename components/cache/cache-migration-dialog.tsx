@@ -20,7 +20,7 @@ import {
 import { cn } from '@/lib/utils';
 import { toast } from 'sonner';
 import { isTauri } from '@/lib/tauri';
-import type { MigrationValidation, MigrationResult } from '@/lib/tauri';
+import type { MigrationValidation, MigrationResult, MigrationProgress } from '@/lib/tauri';
 import type { CacheMigrationDialogProps, MigrationMode } from '@/types/cache';
 
 type Step = 'configure' | 'validated' | 'migrating' | 'done';
@@ -69,6 +69,7 @@ export function CacheMigrationDialog({ open, onOpenChange, onMigrationComplete }
   const [validating, setValidating] = useState(false);
   const [migrating, setMigrating] = useState(false);
   const [result, setResult] = useState<MigrationResult | null>(null);
+  const [progress, setProgress] = useState<MigrationProgress | null>(null);
 
   const step = getStep(validation, migrating, result);
 
@@ -112,8 +113,11 @@ export function CacheMigrationDialog({ open, onOpenChange, onMigrationComplete }
   const handleMigrate = async () => {
     if (!destination.trim() || !isTauri() || !validation?.isValid) return;
     setMigrating(true);
+    setProgress(null);
+    let unlisten: (() => void) | undefined;
     try {
-      const { cacheMigrate } = await import('@/lib/tauri');
+      const { cacheMigrate, listenCacheMigrationProgress } = await import('@/lib/tauri');
+      unlisten = await listenCacheMigrationProgress(setProgress);
       const r = await cacheMigrate(destination.trim(), mode);
       setResult(r);
       if (r.success) {
@@ -125,6 +129,7 @@ export function CacheMigrationDialog({ open, onOpenChange, onMigrationComplete }
     } catch (e) {
       toast.error(t('cache.migrationFailed', { error: String(e) }));
     } finally {
+      unlisten?.();
       setMigrating(false);
     }
   };
@@ -306,7 +311,16 @@ export function CacheMigrationDialog({ open, onOpenChange, onMigrationComplete }
                 <Loader2 className="h-4 w-4 animate-spin text-primary" />
                 {t('cache.migrationMigrating')}
               </div>
-              <Progress className="h-1.5" />
+              <Progress value={progress?.percent} className="h-1.5" />
+              {progress && progress.totalFiles > 0 && (
+                <p className="text-xs tabular-nums text-muted-foreground">
+                  {t('cache.migrationProgressFiles', {
+                    copied: progress.filesCopied,
+                    total: progress.totalFiles,
+                    percent: Math.round(progress.percent),
+                  })}
+                </p>
+              )}
               <p className="text-xs text-muted-foreground">
                 {mode === 'move_and_link'
                   ? t('cache.migrationModeMoveAndLinkDesc')
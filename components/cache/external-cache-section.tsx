@@ -83,6 +83,8 @@ export function ExternalCacheSection({
     loading,
     readState,
     cleaning,
+    forcePreview,
+    forcePreviewLoading,
     cleanableCount,
     totalSize,
     grouped,
@@ -90,6 +92,9 @@ export function ExternalCacheSection({
     fetchExternalCaches,
     handleCleanSingle,
     handleCleanAll,
+    requestForceClean,
+    cancelForceClean,
+    confirmForceClean,
   } = useExternalCache({
     t,
     includePathInfos: false,
@@ -122,6 +127,15 @@ export function ExternalCacheSection({
     }
   };
 
+  const handleConfirmForceClean = async () => {
+    const result = await confirmForceClean();
+    if (result) {
+      setResultRows((rows) =>
+        rows.map((row) => (row.provider === result.provider ? result : row)),
+      );
+    }
+  };
+
   const handleCleanAllWithReport = async () => {
     const results = await handleCleanAll();
     if (results.some((r) => !r.success)) {
@@ -422,6 +436,7 @@ export function ExternalCacheSection({
                   <TableHead>{t("cache.status")}</TableHead>
                   <TableHead>{t("cache.freedSize")}</TableHead>
                   <TableHead>{t("cache.error")}</TableHead>
+                  <TableHead />
                 </TableRow>
               </TableHeader>
               <TableBody>
@@ -447,6 +462,18 @@ export function ExternalCacheSection({
                     </TableCell>
                     <TableCell>{r.freedHuman}</TableCell>
                     <TableCell className="text-xs text-muted-foreground">{r.error ?? ""}</TableCell>
+                    <TableCell>
+                      {!r.success && (
+                        <Button
+                          variant="outline"
+                          size="sm"
+                          disabled={forcePreviewLoading === r.provider || cleaning === r.provider}
+                          onClick={() => void requestForceClean(r.provider)}
+                        >
+                          {t("cache.externalForceAction")}
+                        </Button>
+                      )}
+                    </TableCell>
                   </TableRow>
                 ))}
               </TableBody>
@@ -467,6 +494,46 @@ export function ExternalCacheSection({
           </DialogFooter>
         </DialogContent>
       </Dialog>
+
+      <AlertDialog
+        open={forcePreview !== null}
+        onOpenChange={(open) => {
+          if (!open) cancelForceClean();
+        }}
+      >
+        <AlertDialogContent className="max-w-2xl">
+          <AlertDialogHeader>
+            <AlertDialogTitle>
+              {t("cache.externalForcePreviewTitle", {
+                provider: forcePreview?.displayName ?? "",
+              })}
+            </AlertDialogTitle>
+            <AlertDialogDescription>
+              {t("cache.externalForcePreviewDesc", {
+                size: forcePreview?.totalSizeHuman ?? "",
+                regenerates: forcePreview?.regenerates ?? "",
+              })}
+            </AlertDialogDescription>
+          </AlertDialogHeader>
+          <ul className="max-h-60 overflow-auto rounded-md border p-2 text-xs font-mono space-y-1">
+            {forcePreview?.paths.map((item) => (
+              <li key={item.path} className="flex justify-between gap-4">
+                <span className="truncate">{item.path}</span>
+                <span className="shrink-0 text-muted-foreground">{item.sizeHuman}</span>
+              </li>
+            ))}
+          </ul>
+          <AlertDialogFooter>
+            <AlertDialogCancel>{t("common.cancel")}</AlertDialogCancel>
+            <AlertDialogAction
+              onClick={handleConfirmForceClean}
+              className="bg-destructive text-destructive-foreground hover:bg-destructive/90"
+            >
+              {t("cache.externalForceConfirm")}
+            </AlertDialogAction>
+          </AlertDialogFooter>
+        </AlertDialogContent>
+      </AlertDialog>
     </div>
   );
 }
@@ -0,0 +1,178 @@
+"use client";
+
+import { useEffect, useState, useCallback } from "react";
+import { useRouter } from "next/navigation";
+import { isTauri } from "@/lib/platform";
+import * as tauri from "@/lib/tauri";
+import type { DeepLinkRequest } from "@/types/tauri";
+import {
+  AlertDialog,
+  AlertDialogAction,
+  AlertDialogCancel,
+  AlertDialogContent,
+  AlertDialogDescription,
+  AlertDialogFooter,
+  AlertDialogHeader,
+  AlertDialogTitle,
+} from "@/components/ui/alert-dialog";
+import { Link2 } from "lucide-react";
+import { toast } from "sonner";
+
+interface DeepLinkConfirmDialogProps {
+  t: (key: string, params?: Record<string, string | number>) => string;
+}
+
+function describe(
+  request: DeepLinkRequest,
+  t: DeepLinkConfirmDialogProps["t"],
+): { title: string; details: [string, string][] } {
+  switch (request.action) {
+    case "env_install":
+      return {
+        title: t("deepLink.envInstallTitle", {
+          envType: request.envType,
+          version: request.version,
+        }),
+        details: [
+          [t("deepLink.environment"), request.envType],
+          [t("deepLink.version"), request.version],
+          ...(request.providerId
+            ? [[t("deepLink.provider"), request.providerId] as [string, string]]
+            : []),
+        ],
+      };
+    case "package_install":
+      return {
+        title: t("deepLink.packageInstallTitle", { name: request.name }),
+        details: [
+          [t("deepLink.provider"), request.provider],
+          [t("deepLink.package"), request.name],
+          [t("deepLink.version"), request.version ?? t("deepLink.latest")],
+        ],
+      };
+    case "plugin_install":
+      return {
+        title: t("deepLink.pluginInstallTitle", { id: request.storeId }),
+        details: [[t("deepLink.plugin"), request.storeId]],
+      };
+    case "open_settings":
+      return {
+        title: t("deepLink.openSettingsTitle"),
+        details: request.section
+          ? [[t("deepLink.section"), request.section]]
+          : [],
+      };
+  }
+}
+
+export function DeepLinkConfirmDialog({ t }: DeepLinkConfirmDialogProps) {
+  const router = useRouter();
+  const [queue, setQueue] = useState<DeepLinkRequest[]>([]);
+  const current = queue[0] ?? null;
+
+  useEffect(() => {
+    if (!isTauri()) return;
+
+    let unlisten: (() => void) | undefined;
+    const drain = async () => {
+      try {
+        const pending = await tauri.deeplinkTakePending();
+        if (pending.length > 0) {
+          setQueue((prev) => [...prev, ...pending]);
+        }
+      } catch (err) {
+        console.error("Failed to read pending deep links:", err);
+      }
+    };
+
+    // Links received before the UI was ready wait in the backend queue
+    void drain();
+    tauri.listenDeepLinkReceived(() => void drain()).then((fn) => {
+      unlisten = fn;
+    });
+
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
+  const next = useCallback(() => {
+    setQueue((prev) => prev.slice(1));
+  }, []);
+
+  const handleConfirm = useCallback(async () => {
+    if (!current) return;
+    next();
+    try {
+      switch (current.action) {
+        case "env_install":
+          toast.info(t("deepLink.installStarted", { target: `${current.envType} ${current.version}` }));
+          await tauri.envInstall(
+            current.envType,
+            current.version,
+            current.providerId ?? undefined,
+          );
+          break;
+        case "package_install": {
+          const spec = `${current.provider}:${current.name}${current.version ? `@${current.version}` : ""}`;
+          toast.info(t("deepLink.installStarted", { target: spec }));
+          await tauri.packageInstall([spec]);
+          break;
+        }
+        case "plugin_install":
+          toast.info(t("deepLink.installStarted", { target: current.storeId }));
+          await tauri.pluginInstallMarketplace(current.storeId);
+          break;
+        case "open_settings":
+          router.push(
+            current.section
+              ? `/settings?section=${encodeURIComponent(current.section)}`
+              : "/settings",
+          );
+          return;
+      }
+      toast.success(t("deepLink.installSucceeded"));
+    } catch (err) {
+      toast.error(t("deepLink.actionFailed", { error: String(err) }));
+    }
+  }, [current, next, router, t]);
+
+  if (!current) return null;
+  const { title, details } = describe(current, t);
+
+  return (
+    <AlertDialog open onOpenChange={(open) => !open && next()}>
+      <AlertDialogContent>
+        <AlertDialogHeader>
+          <AlertDialogTitle className="flex items-center gap-2">
+            <Link2 className="h-5 w-5" />
+            {title}
+          </AlertDialogTitle>
+          <AlertDialogDescription>{t("deepLink.description")}</AlertDialogDescription>
+        </AlertDialogHeader>
+        <dl className="grid grid-cols-[auto_1fr] gap-x-4 gap-y-1 text-sm">
+          {details.map(([label, value]) => (
+            <div key={label} className="contents">
+              <dt className="text-muted-foreground">{label}</dt>
+              <dd className="font-mono break-all">{value}</dd>
+            </div>
+          ))}
+        </dl>
+        <p className="text-xs text-muted-foreground break-all">{current.url}</p>
+        <AlertDialogFooter>
+          <AlertDialogCancel>{t("common.cancel")}</AlertDialogCancel>
+          <AlertDialogAction
+            onClick={(event) => {
+              event.preventDefault();
+              void handleConfirm();
+            }}
+          >
+            {current.action === "open_settings"
+              ? t("deepLink.open")
+              : t("deepLink.install")}
+          </AlertDialogAction>
+        </AlertDialogFooter>
+      </AlertDialogContent>
+    </AlertDialog>
+  );
+}
@@ -173,7 +173,8 @@ export function AuthSection({
           )}
           <div className="space-y-2">
             <Label className="text-xs">{t("downloads.auth.token")}</Label>
-            {tokenStatus?.configuredInVault && !token.trim() && (
+            {(tokenStatus?.configuredInStore || tokenStatus?.configuredInVault) &&
+              !token.trim() && (
               <p className="text-xs text-muted-foreground">
                 {t("downloads.auth.savedTokenHidden")}
               </p>
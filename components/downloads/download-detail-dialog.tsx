@@ -97,6 +97,8 @@ function getFailureHintTranslationKey(reasonCode?: string | null): string | null
   switch ((reasonCode ?? "").toLowerCase()) {
     case "checksum_mismatch":
       return "downloads.errors.checksumMismatch";
+    case "verification_failed":
+      return "downloads.errors.verificationFailed";
     case "insufficient_space":
       return "downloads.errors.insufficientSpace";
     case "filesystem_error":
@@ -105,6 +107,8 @@ function getFailureHintTranslationKey(reasonCode?: string | null): string | null
       return "downloads.errors.timeout";
     case "interrupted":
       return "downloads.errors.interrupted";
+    case "artifact_missing":
+      return "downloads.errors.artifactMissing";
     case "invalid_url":
     case "not_found":
     case "forbidden":
@@ -198,7 +198,13 @@ export const DownloadTaskCard = memo(function DownloadTaskCard({
                     </>
                   )}
                   <span>·</span>
-                  <span>{progress.speedHuman || "0 B/s"}</span>
+                  {progress.stalled ? (
+                    <span className="text-amber-600 dark:text-amber-500">
+                      {t("downloads.progress.stalled")}
+                    </span>
+                  ) : (
+                    <span>{progress.speedHuman || "0 B/s"}</span>
+                  )}
                   {progress.etaHuman && (
                     <>
                       <span>·</span>
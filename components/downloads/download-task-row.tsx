@@ -106,7 +106,15 @@ export const DownloadTaskRow = memo(function DownloadTaskRow({
           </div>
         </div>
       </TableCell>
-      <TableCell>{progress.speedHuman || "—"}</TableCell>
+      <TableCell>
+        {progress.stalled ? (
+          <span className="text-amber-600 dark:text-amber-500">
+            {t("downloads.progress.stalled")}
+          </span>
+        ) : (
+          progress.speedHuman || "—"
+        )}
+      </TableCell>
       <TableCell>{formatEta(progress.etaHuman)}</TableCell>
       <TableCell className="text-right">
         <div className="flex items-center justify-end gap-1">
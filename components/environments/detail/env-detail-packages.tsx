@@ -51,6 +51,7 @@ export const ENV_TYPE_TO_PROVIDERS: Record<string, string[]> = {
   clojure: [],
   cpp: ["vcpkg", "conan", "xmake"],
   crystal: [],
+  cuda: ["conda"],
   dart: ["pub"],
   elixir: [],
   erlang: [],
@@ -107,6 +107,11 @@ export function MigratePackagesDialog({
                         disabled={migrating}
                       />
                       <span className="font-mono text-sm flex-1 truncate">{pkg.name}</span>
+                      {pkg.packageManager && (
+                        <Badge variant="secondary" className="text-xs shrink-0">
+                          {pkg.packageManager}
+                        </Badge>
+                      )}
                       <Badge variant="outline" className="font-mono text-xs shrink-0">
                         {pkg.version}
                       </Badge>
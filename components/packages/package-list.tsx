@@ -50,6 +50,7 @@ import {
 import { usePackageStore } from "@/lib/stores/packages";
 import { useLocale } from "@/components/providers/locale-provider";
 import { getPackageKey, isPackageBookmarked, isPackagePinned } from "@/lib/packages";
+import { hasCapability } from "@/lib/constants/provider-capability";
 import { writeClipboard } from "@/lib/clipboard";
 import { toast } from "sonner";
 import type { InstalledPackage, PackageSummary } from "@/lib/tauri";
@@ -70,6 +71,7 @@ export function PackageList({
   onUnpin,
   onRollback,
   onBookmark,
+  providers,
   selectable = true,
   showSelectAll = true,
 }: PackageListProps) {
@@ -79,8 +81,17 @@ export function PackageList({
     togglePackageSelection,
     selectAllPackages,
     clearPackageSelection,
+    providers: storeProviders,
   } = usePackageStore();
   const { t } = useLocale();
+  const knownProviders = providers ?? storeProviders;
+
+  // Actions stay visible when the provider is unknown, so nothing is hidden
+  // before the provider list has loaded
+  const supports = (provider: string | undefined, capability: string) => {
+    const info = provider ? knownProviders.find((p) => p.id === provider) : undefined;
+    return !info || hasCapability(info.capabilities, capability);
+  };
 
   const allSelected =
     packages.length > 0 &&
@@ -169,6 +180,14 @@ export function PackageList({
             const isSelected = selectedPackages.includes(packageKey);
 
             const isPinned = isPackagePinned(pinnedPackages, pkg.name, pkg.provider);
+            const canInstall = supports(pkg.provider, "install");
+            const canUninstall = supports(pkg.provider, "uninstall");
+            const canPin = Boolean(
+              isInstalled && onPin && onUnpin && supports(pkg.provider, "lock_version"),
+            );
+            const canRollback = Boolean(
+              isInstalled && onRollback && supports(pkg.provider, "rollback"),
+            );
 
             return (
               <ContextMenu key={uniqueKey}>
@@ -274,7 +293,7 @@ export function PackageList({
                   </Tooltip>
 
                   {/* Pin/Unpin button for installed packages */}
-                  {isInstalled && onPin && onUnpin && (
+                  {canPin && onPin && onUnpin && (
                     <Tooltip>
                       <TooltipTrigger asChild>
                         <Button
@@ -329,7 +348,7 @@ export function PackageList({
                     </Tooltip>
                   )}
 
-                  {isInstalled ? (
+                  {isInstalled && canUninstall ? (
                     <AlertDialog>
                       <Tooltip>
                         <TooltipTrigger asChild>
@@ -368,7 +387,7 @@ export function PackageList({
                         </AlertDialogFooter>
                       </AlertDialogContent>
                     </AlertDialog>
-                  ) : (
+                  ) : !isInstalled && canInstall ? (
                     <AlertDialog>
                       <Tooltip>
                         <TooltipTrigger asChild>
@@ -415,7 +434,7 @@ export function PackageList({
                         </AlertDialogFooter>
                       </AlertDialogContent>
                     </AlertDialog>
-                  )}
+                  ) : null}
                 </div>
               </div>
                 </ContextMenuTrigger>
@@ -446,7 +465,7 @@ export function PackageList({
                     </ContextMenuItem>
                   )}
                   <ContextMenuSeparator />
-                  {isInstalled ? (
+                  {isInstalled && canUninstall ? (
                     <ContextMenuItem
                       variant="destructive"
                       onClick={() => onUninstall?.(packageKey)}
@@ -454,7 +473,7 @@ export function PackageList({
                       <Trash2 className="h-4 w-4" />
                       {t("common.uninstall")}
                     </ContextMenuItem>
-                  ) : (
+                  ) : !isInstalled && canInstall ? (
                     <ContextMenuItem
                       onClick={() => onInstall?.(packageKey)}
                       disabled={isInstalling}
@@ -462,8 +481,8 @@ export function PackageList({
                       <Download className="h-4 w-4" />
                       {t("common.install")}
                     </ContextMenuItem>
-                  )}
-                  {isInstalled && onPin && onUnpin && (
+                  ) : null}
+                  {canPin && onPin && onUnpin && (
                     <ContextMenuItem
                       onClick={() => isPinned ? onUnpin(pkg.name, pkg.provider) : onPin(pkg.name, pinVersion, pkg.provider)}
                     >
@@ -471,7 +490,7 @@ export function PackageList({
                       {isPinned ? t("packages.unpinVersion") : t("packages.pinVersion")}
                     </ContextMenuItem>
                   )}
-                  {isInstalled && onRollback ? (
+                  {canRollback && onRollback ? (
                     <ContextMenuItem
                       onClick={() => onRollback(pkg.name, pinVersion, pkg.provider)}
                     >
@@ -35,6 +35,7 @@ import {
   ChevronDown,
 } from "lucide-react";
 import { useLocale } from "@/components/providers/locale-provider";
+import { hasCapability } from "@/lib/constants/provider-capability";
 import type { SearchFilters, SearchSuggestion } from "@/lib/tauri";
 import { useDebounce } from "@/hooks/shared/use-mobile";
 import { useSearchHistory } from "@/hooks/packages/use-search-history";
@@ -335,7 +336,7 @@ export function SearchBar({
             </DropdownMenuLabel>
             <DropdownMenuSeparator />
             {providers
-              .filter((p) => p.capabilities.includes("Search"))
+              .filter((p) => hasCapability(p.capabilities, "search"))
               .map((p) => (
                 <DropdownMenuCheckboxItem
                   key={p.id}
@@ -157,6 +157,7 @@ export function PackageOperationPanel({
                   onUnpin={context.onUnpin}
                   onRollback={context.onRollback}
                   onBookmark={context.onBookmark}
+                  providers={context.providers}
                   selectable={resolvedFeatures.batch}
                 />
               </>
@@ -307,6 +308,7 @@ export function PackageOperationPanel({
                   onSelect={context.onSelect}
                   onResolveDependencies={context.onResolveDependencies}
                   onBookmark={context.onBookmark}
+                  providers={context.providers}
                   selectable={Boolean(resolvedFeatures.batch && context.onBatchInstall)}
                   showSelectAll={Boolean(context.onBatchInstall)}
                 />
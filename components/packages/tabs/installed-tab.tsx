@@ -63,6 +63,7 @@ export function InstalledTab({
           onPin={onPin}
           onUnpin={onUnpin}
           onBookmark={onBookmark}
+          providers={providers}
         />
       )}
     </>
@@ -256,10 +256,11 @@ export function PluginIframeView({
   className,
   onStateChange,
 }: PluginIframeViewProps) {
-  const { t } = useLocale();
+  const { t, locale } = useLocale();
   const router = useRouter();
   const { callTool, getLocales, translatePluginKey, getUiAsset } = usePlugins();
   const iframeRef = useRef<HTMLIFrameElement>(null);
+  const lastLocaleRef = useRef(locale);
   const [entry, setEntry] = useState<PluginUiEntry | null>(null);
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
@@ -286,6 +287,16 @@ export function PluginIframeView({
     return () => { cancelled = true; };
   }, [pluginId, getUiAsset]);
 
+  // Notify the plugin UI when the app language changes
+  useEffect(() => {
+    if (lastLocaleRef.current === locale) return;
+    lastLocaleRef.current = locale;
+    iframeRef.current?.contentWindow?.postMessage(
+      { type: 'cognia-event', name: 'locale_changed', payload: { locale } },
+      '*',
+    );
+  }, [locale]);
+
   // Handle postMessage from iframe
   const handleMessage = useCallback(
     async (event: MessageEvent) => {
@@ -40,7 +40,18 @@ import { cn } from '@/lib/utils';
 import { writeClipboard } from '@/lib/clipboard';
 import { useLocale } from '@/components/providers/locale-provider';
 import { DynamicIcon } from '@/components/ui/dynamic-icon';
-import { Copy, Check, Upload, Download, ChevronDown, ChevronUp } from 'lucide-react';
+import { ChartContainer, ChartTooltip, ChartTooltipContent, type ChartConfig } from '@/components/ui/chart';
+import { Line, LineChart, CartesianGrid, XAxis, YAxis } from 'recharts';
+import {
+  Copy,
+  Check,
+  Upload,
+  Download,
+  ChevronDown,
+  ChevronUp,
+  TrendingUp,
+  TrendingDown,
+} from 'lucide-react';
 import type {
   UiBlock,
   UiTextBlock,
@@ -68,6 +79,9 @@ import type {
   UiStepperBlock,
   UiLogStreamBlock,
   UiArtifactActionsBlock,
+  UiLineChartBlock,
+  UiSparklineBlock,
+  UiStatBlock,
   FormField,
   PluginUiAction,
 } from '@/types/plugin-ui';
@@ -155,6 +169,12 @@ function BlockRenderer({ block, onAction, state }: BlockRendererProps) {
       return <LogStreamBlock block={block} onAction={onAction} state={state} />;
     case 'artifact-actions':
       return <ArtifactActionsBlock block={block} onAction={onAction} state={state} />;
+    case 'line-chart':
+      return <LineChartBlock block={block} />;
+    case 'sparkline':
+      return <SparklineBlock block={block} />;
+    case 'stat':
+      return <StatBlock block={block} />;
     case 'group':
       return <GroupBlock block={block} onAction={onAction} state={state} />;
     default:
@@ -1516,6 +1536,140 @@ function ArtifactActionsBlock({
   );
 }
 
+// ============================================================================
+// Chart Blocks
+// ============================================================================
+
+// Same bounds the SDKs enforce; plugin payloads are not trusted to respect them
+const MAX_CHART_POINTS = 500;
+const MAX_CHART_SERIES = 8;
+const SERIES_COLORS = ['var(--chart-1)', 'var(--chart-2)', 'var(--chart-3)', 'var(--chart-4)', 'var(--chart-5)'];
+
+function finitePoints(values: unknown, limit: number): number[] {
+  if (!Array.isArray(values)) return [];
+  return values.slice(-limit).map((v) => (typeof v === 'number' && Number.isFinite(v) ? v : 0));
+}
+
+function LineChartBlock({ block }: { block: UiLineChartBlock }) {
+  const limit = Math.min(block.maxPoints ?? MAX_CHART_POINTS, MAX_CHART_POINTS);
+  const series = (Array.isArray(block.series) ? block.series : [])
+    .slice(0, MAX_CHART_SERIES)
+    .map((s, i) => ({
+      key: `s${i}`,
+      name: s.name,
+      color: s.color ?? SERIES_COLORS[i % SERIES_COLORS.length],
+      values: finitePoints(s.values, limit),
+    }));
+  const length = Math.max(0, ...series.map((s) => s.values.length));
+  const labels = (block.labels ?? []).slice(-limit);
+  const data = Array.from({ length }, (_, idx) => {
+    const point: Record<string, string | number> = { label: labels[idx] ?? String(idx + 1) };
+    for (const s of series) {
+      // Align shorter series to the most recent points
+      const value = s.values[idx - (length - s.values.length)];
+      if (value !== undefined) point[s.key] = value;
+    }
+    return point;
+  });
+  const config: ChartConfig = Object.fromEntries(
+    series.map((s) => [s.key, { label: s.name, color: s.color }]),
+  );
+  const unit = block.yAxis?.unit ?? '';
+
+  return (
+    <div className="space-y-1.5">
+      {block.title && <p className="text-sm font-medium">{block.title}</p>}
+      <ChartContainer config={config} className="h-48 w-full aspect-auto">
+        <LineChart data={data} margin={{ left: 0, right: 8, top: 4, bottom: 0 }}>
+          <CartesianGrid vertical={false} />
+          <XAxis dataKey="label" tickLine={false} axisLine={false} minTickGap={24} />
+          <YAxis
+            width={40}
+            tickLine={false}
+            axisLine={false}
+            domain={[block.yAxis?.min ?? 'auto', block.yAxis?.max ?? 'auto']}
+            tickFormatter={(v) => `${v}${unit}`}
+          />
+          <ChartTooltip content={<ChartTooltipContent formatter={(v) => `${v}${unit}`} />} />
+          {series.map((s) => (
+            <Line
+              key={s.key}
+              type="monotone"
+              dataKey={s.key}
+              name={s.name}
+              stroke={s.color}
+              strokeWidth={1.5}
+              dot={false}
+              isAnimationActive={false}
+            />
+          ))}
+        </LineChart>
+      </ChartContainer>
+    </div>
+  );
+}
+
+function SparklineBlock({ block }: { block: UiSparklineBlock }) {
+  const data = finitePoints(block.values, MAX_CHART_POINTS).map((value, idx) => ({ idx, value }));
+  if (data.length < 2) return null;
+  return (
+    <ChartContainer
+      config={{ value: { color: 'var(--chart-2)' } }}
+      className="h-8 w-32 aspect-auto"
+    >
+      <LineChart data={data} margin={{ left: 0, right: 0, top: 2, bottom: 2 }}>
+        <YAxis hide domain={['auto', 'auto']} />
+        <Line
+          type="monotone"
+          dataKey="value"
+          stroke="var(--chart-2)"
+          strokeWidth={1.5}
+          dot={false}
+          isAnimationActive={false}
+        />
+      </LineChart>
+    </ChartContainer>
+  );
+}
+
+/** Display text for a stat value; payloads may carry any JSON value */
+function formatStatValue(value: unknown): string {
+  if (value === null || value === undefined) return '—';
+  if (typeof value === 'string' || typeof value === 'number' || typeof value === 'boolean') {
+    return String(value);
+  }
+  try {
+    return JSON.stringify(value);
+  } catch {
+    return String(value);
+  }
+}
+
+function StatBlock({ block }: { block: UiStatBlock }) {
+  const delta = typeof block.delta === 'number' && Number.isFinite(block.delta) ? block.delta : null;
+  return (
+    <div className="rounded-md border p-3 space-y-1.5">
+      <span className="text-xs text-muted-foreground">{block.label}</span>
+      <div className="flex items-baseline gap-2">
+        <p className="text-2xl font-semibold leading-none break-all">
+          {formatStatValue(block.value)}
+        </p>
+        {delta !== null && delta !== 0 && (
+          <span
+            className={cn(
+              'flex items-center gap-0.5 text-xs',
+              delta > 0 ? 'text-green-600' : 'text-red-600',
+            )}
+          >
+            {delta > 0 ? <TrendingUp className="h-3 w-3" /> : <TrendingDown className="h-3 w-3" />}
+            {delta > 0 ? `+${delta}` : delta}
+          </span>
+        )}
+      </div>
+    </div>
+  );
+}
+
 // ============================================================================
 // Layout Block
 // ============================================================================
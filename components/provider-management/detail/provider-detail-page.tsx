@@ -17,6 +17,7 @@ import {
 } from '@/components/provider-management/detail';
 import { useProviderDetail } from '@/hooks/providers/use-provider-detail';
 import { useLocale } from '@/components/providers/locale-provider';
+import { hasCapability } from '@/lib/constants/provider-capability';
 import {
   AlertCircle,
   LayoutDashboard,
@@ -179,6 +180,14 @@ export function ProviderDetailPageClient({ providerId }: ProviderDetailPageClien
   if (!provider) return null;
 
   const isEnvironmentProvider = provider.is_environment_provider;
+  const canUpdate =
+    hasCapability(provider.capabilities, 'update') ||
+    hasCapability(provider.capabilities, 'upgrade');
+  const requestedTab = searchParams.get('tab');
+  const initialTab =
+    requestedTab === 'packages' || (requestedTab === 'updates' && canUpdate)
+      ? requestedTab
+      : 'overview';
 
   return (
     <div className="p-4 md:p-6 space-y-6">
@@ -201,7 +210,7 @@ export function ProviderDetailPageClient({ providerId }: ProviderDetailPageClien
         </Alert>
       )}
 
-      <Tabs defaultValue="overview" className="space-y-4">
+      <Tabs defaultValue={initialTab} className="space-y-4">
         <TabsList className="flex-wrap h-auto gap-1">
           <TabsTrigger value="overview" className="gap-1.5">
             <LayoutDashboard className="h-4 w-4" />
@@ -216,15 +225,17 @@ export function ProviderDetailPageClient({ providerId }: ProviderDetailPageClien
               </Badge>
             )}
           </TabsTrigger>
-          <TabsTrigger value="updates" className="gap-1.5">
-            <ArrowUpCircle className="h-4 w-4" />
-            {t('providerDetail.tabUpdates')}
-            {availableUpdates.length > 0 && (
-              <Badge variant="secondary" className="ml-1 text-xs h-5 px-1.5 bg-orange-500/20 text-orange-700 dark:text-orange-300">
-                {availableUpdates.length}
-              </Badge>
-            )}
-          </TabsTrigger>
+          {canUpdate && (
+            <TabsTrigger value="updates" className="gap-1.5">
+              <ArrowUpCircle className="h-4 w-4" />
+              {t('providerDetail.tabUpdates')}
+              {availableUpdates.length > 0 && (
+                <Badge variant="secondary" className="ml-1 text-xs h-5 px-1.5 bg-orange-500/20 text-orange-700 dark:text-orange-300">
+                  {availableUpdates.length}
+                </Badge>
+              )}
+            </TabsTrigger>
+          )}
           <TabsTrigger value="health" className="gap-1.5">
             <ShieldCheck className="h-4 w-4" />
             {t('providerDetail.tabHealth')}
@@ -263,6 +274,7 @@ export function ProviderDetailPageClient({ providerId }: ProviderDetailPageClien
         <TabsContent value="packages">
           <ProviderPackagesTab
             providerId={providerId}
+            capabilities={provider.capabilities}
             installedPackages={installedPackages}
             searchResults={searchResults}
             searchQuery={searchQuery}
@@ -286,15 +298,17 @@ export function ProviderDetailPageClient({ providerId }: ProviderDetailPageClien
           />
         </TabsContent>
 
-        <TabsContent value="updates">
-          <ProviderUpdatesTab
-            availableUpdates={availableUpdates}
-            loadingUpdates={loadingUpdates}
-            onCheckUpdates={checkUpdates}
-            onUpdatePackage={updatePackage}
-            onUpdateAllPackages={updateAllPackages}
-          />
-        </TabsContent>
+        {canUpdate && (
+          <TabsContent value="updates">
+            <ProviderUpdatesTab
+              availableUpdates={availableUpdates}
+              loadingUpdates={loadingUpdates}
+              onCheckUpdates={checkUpdates}
+              onUpdatePackage={updatePackage}
+              onUpdateAllPackages={updateAllPackages}
+            />
+          </TabsContent>
+        )}
 
         <TabsContent value="health">
           <ProviderHealthTab
@@ -29,9 +29,12 @@ import {
 } from "@/components/packages/shared/package-operation-context";
 import { parsePackageSpec } from "@/lib/packages";
 import { usePackageStore } from "@/lib/stores/packages";
+import { hasCapability } from "@/lib/constants/provider-capability";
 
 interface ProviderPackagesTabProps {
   providerId: string;
+  /** Declared capabilities; actions outside them are hidden */
+  capabilities: string[];
   installedPackages: InstalledPackage[];
   searchResults: PackageSummary[];
   searchQuery: string;
@@ -54,11 +57,11 @@ interface ProviderPackagesTabProps {
   onDismissPreflight?: () => void;
 }
 
-function toProviderInfo(providerId: string): ProviderInfo {
+function toProviderInfo(providerId: string, capabilities: string[]): ProviderInfo {
   return {
     id: providerId,
     display_name: providerId,
-    capabilities: ["Search", "Install"],
+    capabilities,
     platforms: ["Windows", "Linux", "macOS"],
     priority: 1,
     is_environment_provider: true,
@@ -68,6 +71,7 @@ function toProviderInfo(providerId: string): ProviderInfo {
 
 export function ProviderPackagesTab({
   providerId,
+  capabilities,
   installedPackages,
   searchResults,
   loadingPackages,
@@ -94,7 +98,12 @@ export function ProviderPackagesTab({
   const selectedPackages = usePackageStore((state) => state.selectedPackages);
   const clearPackageSelection = usePackageStore((state) => state.clearPackageSelection);
 
-  const providers = useMemo(() => [toProviderInfo(providerId)], [providerId]);
+  const providers = useMemo(
+    () => [toProviderInfo(providerId, capabilities)],
+    [capabilities, providerId],
+  );
+  const canSearch = hasCapability(capabilities, "search");
+  const canBatchUninstall = Boolean(onBatchUninstall) && hasCapability(capabilities, "uninstall");
   const installing = useMemo(
     () => [...installingPackages, ...uninstallingPackages].map((name) => `${providerId}:${name}`),
     [installingPackages, providerId, uninstallingPackages],
@@ -186,7 +195,7 @@ export function ProviderPackagesTab({
   );
 
   const bottomContent =
-    onBatchUninstall && selectedPackages.length > 0 ? (
+    onBatchUninstall && canBatchUninstall && selectedPackages.length > 0 ? (
       <BatchOperations
         selectedPackages={selectedPackages}
         allowedOperations={["uninstall"]}
@@ -225,7 +234,8 @@ export function ProviderPackagesTab({
     mode: "provider",
     features: {
       updates: false,
-      batch: Boolean(onBatchUninstall),
+      search: canSearch,
+      batch: canBatchUninstall,
       installedFilter: installedPackages.length > 5,
       pinning: Boolean(onPinPackage || onUnpinPackage),
     },
@@ -265,7 +275,7 @@ export function ProviderPackagesTab({
     <PackageOperationProvider value={contextValue}>
       <PackageOperationPanel
         mode="provider"
-        features={{ updates: false, batch: Boolean(onBatchUninstall) }}
+        features={{ updates: false, search: canSearch, batch: canBatchUninstall }}
       />
     </PackageOperationProvider>
   );
@@ -10,12 +10,22 @@ import {
   DropdownMenuSeparator,
   DropdownMenuTrigger,
 } from "@/components/ui/dropdown-menu";
-import { Activity, ExternalLink, MoreHorizontal, Copy } from "lucide-react";
+import {
+  Activity,
+  ArrowUpCircle,
+  ExternalLink,
+  MoreHorizontal,
+  Copy,
+  Search,
+} from "lucide-react";
 import { useLocale } from "@/components/providers/locale-provider";
+import { hasCapability } from "@/lib/constants/provider-capability";
 import { toast } from "sonner";
 
 interface ProviderActionsMenuProps {
   providerId: string;
+  /** Declared capabilities; actions outside them are hidden */
+  capabilities: string[];
   detailHref: string;
   isChecking: boolean;
   onCheckStatus: () => void;
@@ -24,6 +34,7 @@ interface ProviderActionsMenuProps {
 
 export function ProviderActionsMenu({
   providerId,
+  capabilities,
   detailHref,
   isChecking,
   onCheckStatus,
@@ -32,6 +43,11 @@ export function ProviderActionsMenu({
   const { t } = useLocale();
   const buttonClass =
     triggerSize === "sm" ? "h-7 w-7 p-0" : "h-8 w-8 p-0";
+  const tabHref = (tab: string) =>
+    `${detailHref}${detailHref.includes("?") ? "&" : "?"}tab=${tab}`;
+  const canSearch = hasCapability(capabilities, "search");
+  const canUpdate =
+    hasCapability(capabilities, "update") || hasCapability(capabilities, "upgrade");
 
   return (
     <DropdownMenu>
@@ -55,6 +71,23 @@ export function ProviderActionsMenu({
           <Copy className="h-4 w-4 mr-2" />
           {t("providers.copyId")}
         </DropdownMenuItem>
+        {(canSearch || canUpdate) && <DropdownMenuSeparator />}
+        {canSearch && (
+          <DropdownMenuItem asChild>
+            <Link href={tabHref("packages")}>
+              <Search className="h-4 w-4 mr-2" />
+              {t("providerDetail.searchPackages")}
+            </Link>
+          </DropdownMenuItem>
+        )}
+        {canUpdate && (
+          <DropdownMenuItem asChild>
+            <Link href={tabHref("updates")}>
+              <ArrowUpCircle className="h-4 w-4 mr-2" />
+              {t("providerDetail.checkUpdates")}
+            </Link>
+          </DropdownMenuItem>
+        )}
         <DropdownMenuSeparator />
         <DropdownMenuItem asChild>
           <Link href={detailHref}>
@@ -87,6 +87,7 @@ export function ProviderCard({
             <ProviderStatusBadge status={resolvedStatus} />
             <ProviderActionsMenu
               providerId={provider.id}
+              capabilities={provider.capabilities}
               detailHref={detailHref}
               isChecking={isChecking}
               onCheckStatus={handleCheckStatus}
@@ -108,6 +108,7 @@ export function ProviderListItem({
 
         <ProviderActionsMenu
           providerId={provider.id}
+          capabilities={provider.capabilities}
           detailHref={detailHref}
           isChecking={isChecking}
           onCheckStatus={handleCheckStatus}
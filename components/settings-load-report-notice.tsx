@@ -0,0 +1,68 @@
+"use client";
+
+import { useEffect } from "react";
+import { isTauri } from "@/lib/platform";
+import * as tauri from "@/lib/tauri";
+import type { SettingsLoadReport } from "@/types/tauri";
+import { toast } from "sonner";
+
+interface SettingsLoadReportNoticeProps {
+  t: (key: string, params?: Record<string, string | number>) => string;
+}
+
+function summarize(
+  report: SettingsLoadReport,
+  t: SettingsLoadReportNoticeProps["t"],
+): string[] {
+  const lines: string[] = [];
+  if (report.issues.some((issue) => issue.key === "")) {
+    lines.push(t("settings.loadReportUnreadable"));
+  } else {
+    if (report.fromVersion < report.toVersion) {
+      lines.push(
+        t("settings.loadReportMigrated", {
+          from: report.fromVersion,
+          to: report.toVersion,
+        }),
+      );
+    }
+    if (report.issues.length > 0) {
+      lines.push(t("settings.loadReportReset", { count: report.issues.length }));
+    }
+  }
+  if (report.backupPath) {
+    lines.push(t("settings.loadReportBackup", { path: report.backupPath }));
+  }
+  return lines;
+}
+
+/** Tells the user once per launch when the config file was migrated or repaired */
+export function SettingsLoadReportNotice({ t }: SettingsLoadReportNoticeProps) {
+  useEffect(() => {
+    if (!isTauri()) return;
+
+    tauri
+      .configTakeLoadReport()
+      .then((report) => {
+        if (!report) return;
+        for (const issue of report.issues) {
+          console.warn(
+            `Settings value ${issue.key || "(file)"} reset: ${issue.message}`,
+          );
+        }
+        const [title, ...details] = summarize(report, t);
+        if (!title) return;
+        toast.warning(title, {
+          description: details.join(" "),
+          duration: 10000,
+        });
+      })
+      .catch((err) => {
+        console.error("Failed to read settings load report:", err);
+      });
+    // Only taken once; the backend clears the report after the first read
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, []);
+
+  return null;
+}
@@ -183,6 +183,28 @@ export function GeneralSettings({
           max={32}
           error={errors["general.update_check_concurrency"]}
         />
+        <Separator />
+        <SwitchSettingItem
+          id="metrics-listener-enabled"
+          label={t("settings.metricsListenerEnabled")}
+          description={t("settings.metricsListenerEnabledDesc")}
+          checked={localConfig["general.metrics_listener_enabled"] === "true"}
+          onCheckedChange={(checked) =>
+            onValueChange("general.metrics_listener_enabled", checked.toString())
+          }
+        />
+        <Separator />
+        <SettingItem
+          id="metrics-listener-port"
+          label={t("settings.metricsListenerPort")}
+          description={t("settings.metricsListenerPortDesc")}
+          value={localConfig["general.metrics_listener_port"] || "9464"}
+          onChange={(v) => onValueChange("general.metrics_listener_port", v)}
+          type="number"
+          min={1024}
+          max={65535}
+          error={errors["general.metrics_listener_port"]}
+        />
     </div>
   );
 }
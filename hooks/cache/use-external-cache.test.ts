@@ -0,0 +1,124 @@
+import { act, renderHook } from '@testing-library/react';
+import { useExternalCache } from './use-external-cache';
+
+const mockCleanExternalCache = jest.fn();
+const mockCleanAllExternalCaches = jest.fn();
+const mockCleanExternalCachePreview = jest.fn();
+const mockCacheForceCleanExternal = jest.fn();
+
+jest.mock('@/lib/tauri', () => ({
+  isTauri: () => true,
+  cleanExternalCache: (...args: unknown[]) => mockCleanExternalCache(...args),
+  cleanAllExternalCaches: (...args: unknown[]) => mockCleanAllExternalCaches(...args),
+  cleanExternalCachePreview: (...args: unknown[]) => mockCleanExternalCachePreview(...args),
+  cacheForceCleanExternal: (...args: unknown[]) => mockCacheForceCleanExternal(...args),
+  getScanSettings: jest.fn().mockResolvedValue({ probe_concurrency: 1 }),
+  discoverExternalCachesFast: jest.fn().mockResolvedValue([]),
+}));
+
+jest.mock('@/lib/cache/invalidation', () => ({
+  emitInvalidations: jest.fn(),
+  ensureCacheInvalidationBridge: jest.fn().mockResolvedValue(undefined),
+  subscribeInvalidation: jest.fn(() => () => {}),
+  withThrottle: (fn: () => void) => fn,
+}));
+
+jest.mock('sonner', () => ({
+  toast: { success: jest.fn(), error: jest.fn(), warning: jest.fn() },
+}));
+
+const t = (key: string) => key;
+
+const failed = (provider: string) => ({
+  provider,
+  displayName: provider,
+  freedBytes: 0,
+  freedHuman: '0 B',
+  success: false,
+  error: 'tool failed',
+});
+
+const preview = {
+  provider: 'npm',
+  displayName: 'npm',
+  cachePath: '/cache/npm',
+  totalSize: 2048,
+  totalSizeHuman: '2 KB',
+  paths: [{ path: '/cache/npm/_cacache', size: 2048, sizeHuman: '2 KB', cost: 'cheap' }],
+  cost: 'cheap',
+  regenerates: 'npm install',
+  modes: [],
+  token: 'preview-token',
+  tokenExpiresAt: '2026-01-01T00:00:00Z',
+};
+
+describe('useExternalCache force clean', () => {
+  beforeEach(() => {
+    mockCleanExternalCachePreview.mockResolvedValue(preview);
+    mockCacheForceCleanExternal.mockResolvedValue({
+      ...failed('npm'),
+      success: true,
+      error: null,
+      freedBytes: 2048,
+      freedHuman: '2 KB',
+    });
+  });
+
+  it('does not fall back to force clean when a direct clean fails', async () => {
+    mockCleanExternalCache.mockResolvedValue(failed('npm'));
+    mockCleanAllExternalCaches.mockResolvedValue([failed('npm'), failed('pip')]);
+    const { result } = renderHook(() => useExternalCache({ t }));
+
+    await act(async () => {
+      await result.current.handleCleanSingle('npm');
+      await result.current.handleCleanAll();
+    });
+
+    expect(mockCleanExternalCachePreview).not.toHaveBeenCalled();
+    expect(mockCacheForceCleanExternal).not.toHaveBeenCalled();
+  });
+
+  it('never executes without a confirmed preview', async () => {
+    const { result } = renderHook(() => useExternalCache({ t }));
+
+    await act(async () => {
+      expect(await result.current.confirmForceClean()).toBeNull();
+    });
+    expect(mockCacheForceCleanExternal).not.toHaveBeenCalled();
+
+    await act(async () => {
+      await result.current.requestForceClean('npm');
+    });
+    expect(result.current.forcePreview).toEqual(preview);
+    expect(mockCacheForceCleanExternal).not.toHaveBeenCalled();
+
+    act(() => {
+      result.current.cancelForceClean();
+    });
+    await act(async () => {
+      await result.current.confirmForceClean();
+    });
+    expect(mockCacheForceCleanExternal).not.toHaveBeenCalled();
+  });
+
+  it('executes with the token of the confirmed preview', async () => {
+    const { result } = renderHook(() => useExternalCache({ t, useTrash: false }));
+
+    await act(async () => {
+      await result.current.requestForceClean('npm');
+    });
+    await act(async () => {
+      await result.current.confirmForceClean();
+    });
+
+    expect(mockCacheForceCleanExternal).toHaveBeenCalledTimes(1);
+    expect(mockCacheForceCleanExternal).toHaveBeenCalledWith(
+      'npm',
+      'preview-token',
+      undefined,
+      false,
+      false,
+    );
+    expect(result.current.forcePreview).toBeNull();
+  });
+});
@@ -8,6 +8,7 @@ import type {
   ExternalCacheCleanResult,
   ExternalCacheInfo,
   ExternalCachePathInfo,
+  ExternalCleanPreview,
 } from '@/lib/tauri';
 import { formatBytes } from '@/lib/utils';
 import { CACHE_CATEGORY_ORDER, groupCachesByCategory } from '@/lib/constants/cache';
@@ -150,6 +151,11 @@ export function useExternalCache({
   const [cleanTarget, setCleanTarget] = useState<string | null>(null);
   const [cleanAllOpen, setCleanAllOpen] = useState(false);
   const [cleaning, setCleaning] = useState<string | null>(null);
+  // Force clean deletes files directly, so it only runs with a preview the
+  // user has seen and confirmed; the preview token is never fetched and
+  // spent in one step.
+  const [forcePreview, setForcePreview] = useState<ExternalCleanPreview | null>(null);
+  const [forcePreviewLoading, setForcePreviewLoading] = useState<string | null>(null);
 
   const resolvedUseTrash = useTrash ?? internalUseTrash;
   const handleUseTrashChange = useCallback((next: boolean) => {
@@ -435,65 +441,21 @@ export function useExternalCache({
     };
   }, [fetchExternalCaches]);
 
-  const cleanSingleWithFallback = useCallback(async (provider: string) => {
-    const { cleanExternalCache, cacheForceCleanExternal } = await import('@/lib/tauri');
+  const cleanSingle = useCallback(async (provider: string) => {
+    const { cleanExternalCache } = await import('@/lib/tauri');
 
     try {
-      const directResult = await cleanExternalCache(provider, resolvedUseTrash);
-      if (directResult.success) {
-        return directResult;
-      }
-
-      try {
-        return await cacheForceCleanExternal(provider, false, resolvedUseTrash);
-      } catch (fallbackErr) {
-        return {
-          ...directResult,
-          success: false,
-          error: directResult.error ?? String(fallbackErr),
-        };
-      }
+      return await cleanExternalCache(provider, resolvedUseTrash);
     } catch (err) {
       return makeFailureResult(provider, String(err));
     }
   }, [resolvedUseTrash]);
 
-  const cleanAllWithFallback = useCallback(async () => {
-    const { cleanAllExternalCaches, cacheForceCleanExternal } = await import('@/lib/tauri');
-
-    let results = await cleanAllExternalCaches(resolvedUseTrash);
-    const failedProviders = results
-      .filter((result) => !result.success)
-      .map((result) => result.provider);
-
-    if (failedProviders.length === 0) {
-      return results;
-    }
-
-    const forcedResults = await Promise.all(
-      failedProviders.map(async (provider) => {
-        try {
-          return await cacheForceCleanExternal(provider, false, resolvedUseTrash);
-        } catch (err) {
-          return makeFailureResult(provider, String(err));
-        }
-      }),
-    );
-
-    const forcedMap = new Map(forcedResults.map((result) => [result.provider, result]));
-    results = results.map((result) => {
-      if (result.success) return result;
-      return forcedMap.get(result.provider) ?? result;
-    });
-
-    return results;
-  }, [resolvedUseTrash]);
-
   const handleCleanSingle = useCallback(async (provider: string) => {
     if (!isTauri()) return null;
     setCleaning(provider);
     try {
-      const result = await cleanSingleWithFallback(provider);
+      const result = await cleanSingle(provider);
       if (result.success) {
         toast.success(t('cache.externalCleanSuccess', {
           provider: result.displayName,
@@ -515,13 +477,14 @@ export function useExternalCache({
       setCleaning(null);
       setCleanTarget(null);
     }
-  }, [cleanSingleWithFallback, fetchExternalCaches, t]);
+  }, [cleanSingle, fetchExternalCaches, t]);
 
   const handleCleanAll = useCallback(async () => {
     if (!isTauri()) return [];
     setCleaning('all');
     try {
-      const results = await cleanAllWithFallback();
+      const { cleanAllExternalCaches } = await import('@/lib/tauri');
+      const results = await cleanAllExternalCaches(resolvedUseTrash);
       const successCount = results.filter((result) => result.success).length;
       const totalFreed = results.reduce((acc, result) => acc + result.freedBytes, 0);
 
@@ -556,7 +519,65 @@ export function useExternalCache({
       setCleaning(null);
       setCleanAllOpen(false);
     }
-  }, [cleanAllWithFallback, fetchExternalCaches, t]);
+  }, [fetchExternalCaches, resolvedUseTrash, t]);
+
+  /** Step 1 of force clean: load the preview (and its token) for the confirm dialog. */
+  const requestForceClean = useCallback(async (provider: string) => {
+    if (!isTauri()) return null;
+    setForcePreviewLoading(provider);
+    try {
+      const { cleanExternalCachePreview } = await import('@/lib/tauri');
+      const preview = await cleanExternalCachePreview(provider);
+      setForcePreview(preview);
+      return preview;
+    } catch (err) {
+      toast.error(t('cache.externalCleanFailed', { provider, error: String(err) }));
+      return null;
+    } finally {
+      setForcePreviewLoading(null);
+    }
+  }, [t]);
+
+  const cancelForceClean = useCallback(() => {
+    setForcePreview(null);
+  }, []);
+
+  /** Step 2 of force clean: run it with the token of the preview the user confirmed. */
+  const confirmForceClean = useCallback(async () => {
+    const preview = forcePreview;
+    if (!isTauri() || !preview) return null;
+    setForcePreview(null);
+    setCleaning(preview.provider);
+    try {
+      const { cacheForceCleanExternal } = await import('@/lib/tauri');
+      const result = await cacheForceCleanExternal(
+        preview.provider,
+        preview.token,
+        undefined,
+        false,
+        resolvedUseTrash,
+      ).catch((err) => makeFailureResult(preview.provider, String(err)));
+      if (result.success) {
+        toast.success(t('cache.externalCleanSuccess', {
+          provider: result.displayName,
+          size: result.freedHuman,
+        }));
+        emitInvalidations(
+          ['external_cache', 'cache_overview', 'about_cache_stats'],
+          'external-cache:force-clean',
+        );
+      } else {
+        toast.error(t('cache.externalCleanFailed', {
+          provider: result.displayName,
+          error: result.error || 'Unknown error',
+        }));
+      }
+      await fetchExternalCaches();
+      return result;
+    } finally {
+      setCleaning(null);
+    }
+  }, [fetchExternalCaches, forcePreview, resolvedUseTrash, t]);
 
   const getPathInfo = useCallback((provider: string) => {
     return pathInfos.find((pathInfo) => pathInfo.provider === provider);
@@ -586,6 +607,8 @@ export function useExternalCache({
     cleanAllOpen,
     setCleanAllOpen,
     cleaning,
+    forcePreview,
+    forcePreviewLoading,
 
     totalSize,
     availableCount,
@@ -596,6 +619,9 @@ export function useExternalCache({
     fetchExternalCaches,
     handleCleanSingle,
     handleCleanAll,
+    requestForceClean,
+    cancelForceClean,
+    confirmForceClean,
     getPathInfo,
   };
 }
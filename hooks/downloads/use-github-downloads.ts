@@ -174,16 +174,21 @@ export function useGitHubDownloads(): UseGitHubDownloadsReturn {
 
       const tauri = await import('@/lib/tauri');
       const authToken = token.trim() || undefined;
+      // The release tag lets the backend find published checksums for the asset
+      const tag = releases.find((release) =>
+        release.assets.some((candidate) => candidate.id === asset.id)
+      )?.tagName;
       return tauri.githubDownloadAsset(
         parsedRepo.fullName,
         asset.id,
         asset.downloadUrl,
         asset.name,
         destination,
-        authToken
+        authToken,
+        tag
       );
     },
-    [parsedRepo, token]
+    [parsedRepo, token, releases]
   );
 
   const downloadSource = useCallback(
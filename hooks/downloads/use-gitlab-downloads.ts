@@ -199,16 +199,21 @@ export function useGitLabDownloads(): UseGitLabDownloadsReturn {
       const url = asset.directAssetUrl || asset.url;
       const authToken = token.trim() || undefined;
       const instUrl = instanceUrl.trim() || undefined;
+      // The release tag lets the backend find published checksums for the asset
+      const tag = releases.find((release) =>
+        release.assets.some((candidate) => candidate.id === asset.id)
+      )?.tagName;
       return tauri.gitlabDownloadAsset(
         parsedProject.fullName,
         url,
         asset.name,
         destination,
         authToken,
-        instUrl
+        instUrl,
+        tag
       );
     },
-    [parsedProject, token, instanceUrl]
+    [parsedProject, token, instanceUrl, releases]
   );
 
   const downloadSource = useCallback(
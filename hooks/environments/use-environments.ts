@@ -809,7 +809,7 @@ export function useEnvironments() {
 
     try {
       const request = tauri.envAvailableVersions(envType, providerId, force)
-        .then((versions) => {
+        .then(({ versions }) => {
           useEnvironmentStore.getState().setAvailableVersions(cacheKey, versions);
           return versions;
         })
@@ -37,6 +37,7 @@ export function useVersionBrowser(
   const [installingVersion, setInstallingVersion] = useState<string | null>(null);
   const [batchProcessing, setBatchProcessing] = useState(false);
   const [displayLimit, setDisplayLimit] = useState(50);
+  const [lastSynced, setLastSynced] = useState<string | null>(null);
 
   const versionCacheKey = useMemo(
     () => (providerId ? `${envType}::${providerId}` : envType),
@@ -75,8 +76,12 @@ export function useVersionBrowser(
       setLoading(true);
       setError(null);
       try {
-        const result = await tauri.envAvailableVersions(envType, providerId);
-        setAvailableVersions(versionCacheKey, result);
+        // A forced refresh syncs with upstream instead of reading the cache
+        const result = force
+          ? await tauri.envRefreshVersions(envType, false, providerId)
+          : await tauri.envAvailableVersions(envType, providerId);
+        setAvailableVersions(versionCacheKey, result.versions);
+        setLastSynced(result.lastSynced);
       } catch (err) {
         const errorMsg = err instanceof Error ? err.message : String(err);
         setError(errorMsg);
@@ -286,6 +291,7 @@ export function useVersionBrowser(
     batchProcessing,
     // Derived
     versions,
+    lastSynced,
     displayVersions,
     hasMore,
     totalFiltered,
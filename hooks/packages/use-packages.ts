@@ -69,7 +69,7 @@ export function usePackages() {
     store.setError(null);
     store.setSearchQuery(query);
     try {
-      const results = await tauri.packageSearch(query, provider);
+      const { data: results } = await tauri.packageSearch(query, provider);
       store.setSearchResults(results);
       return results;
     } catch (err) {
@@ -126,7 +126,7 @@ export function usePackages() {
     store.setLoading(true);
     store.setError(null);
     try {
-      const info = await tauri.packageInfo(name, provider);
+      const { data: info } = await tauri.packageInfo(name, provider);
       store.setSelectedPackage(info);
       return info;
     } catch (err) {
@@ -827,8 +827,11 @@ export function usePlugins() {
       params?: Record<string, string>,
     ): string => {
       if (!locales) return key;
-      const strings = locales[locale] ?? locales.en ?? {};
-      let text = strings[key] ?? key;
+      // Same chain as the host: locale -> language only -> en -> raw key
+      const chain = [locale, locale.split(/[-_]/)[0], 'en'];
+      let text = chain
+        .map((tag) => locales[tag]?.[key])
+        .find((value): value is string => value !== undefined) ?? key;
       if (params) {
         for (const [k, v] of Object.entries(params)) {
           text = text.replace(`{${k}}`, v);
@@ -553,7 +553,7 @@ export function useProviderDetail(providerId: string) {
 
         // Fetch available versions
         try {
-          const versions = await tauri.envAvailableVersions(
+          const { versions } = await tauri.envAvailableVersions(
             envProvider.env_type,
             providerId,
           );
@@ -35,6 +35,7 @@ export const LANGUAGES = [
   { id: 'nim', name: 'Nim', icon: '👑', color: 'bg-yellow-600/10 border-yellow-600 dark:bg-yellow-600/20' },
   { id: 'ocaml', name: 'OCaml', icon: '🐫', color: 'bg-orange-600/10 border-orange-600 dark:bg-orange-600/20' },
   { id: 'fortran', name: 'Fortran', icon: '🔢', color: 'bg-purple-700/10 border-purple-700 dark:bg-purple-700/20' },
+  { id: 'cuda', name: 'CUDA', icon: '🟩', color: 'bg-lime-600/10 border-lime-600 dark:bg-lime-600/20' },
 ] as const;
 
 export type LanguageId = typeof LANGUAGES[number]['id'];
@@ -137,6 +138,7 @@ export const DEFAULT_PROVIDERS: Record<string, { id: string; name: string; descr
     { id: 'mise', name: 'mise', description: 'Polyglot version manager' },
   ],
   julia: [
+    { id: 'juliaup', name: 'juliaup', description: 'Official Julia version multiplexer' },
     { id: 'asdf', name: 'asdf', description: 'Extendable version manager' },
     { id: 'mise', name: 'mise', description: 'Polyglot version manager' },
   ],
@@ -145,6 +147,7 @@ export const DEFAULT_PROVIDERS: Record<string, { id: string; name: string; descr
     { id: 'mise', name: 'mise', description: 'Polyglot version manager' },
   ],
   r: [
+    { id: 'rig', name: 'rig', description: 'R installation manager' },
     { id: 'asdf', name: 'asdf', description: 'Extendable version manager' },
     { id: 'mise', name: 'mise', description: 'Polyglot version manager' },
   ],
@@ -179,15 +182,27 @@ export const DEFAULT_DETECTION_FILES: Record<string, string[]> = {
   node: ['.nvmrc', '.node-version', '.tool-versions', 'package.json (volta.node)', 'package.json (engines.node)', 'mise.toml'],
   python: [
     '.python-version',
+    'pyproject.toml (tool.uv.python)',
     'pyproject.toml (project.requires-python)',
     'pyproject.toml (tool.poetry.dependencies.python)',
     'uv.toml (requires-python)',
+    'pixi.toml (dependencies.python)',
+    'pixi.lock (python)',
+    'conda-lock.yml (python)',
     'Pipfile (requires.python_version)',
     'runtime.txt',
     '.tool-versions',
     'mise.toml',
   ],
-  go: ['go.mod (toolchain)', 'go.mod (go)', '.go-version', '.tool-versions', 'mise.toml'],
+  go: [
+    'go.work (toolchain)',
+    'go.work (go)',
+    'go.mod (toolchain)',
+    'go.mod (go)',
+    '.go-version',
+    '.tool-versions',
+    'mise.toml',
+  ],
   rust: ['rust-toolchain', 'rust-toolchain.toml', 'Cargo.toml (rust-version)', '.tool-versions', 'mise.toml'],
   ruby: ['.ruby-version', 'Gemfile', '.tool-versions', 'mise.toml'],
   java: [
@@ -237,6 +252,7 @@ export const DEFAULT_DETECTION_FILES: Record<string, string[]> = {
   nim: ['.nim-version', 'nimble (nim)', '.tool-versions', 'mise.toml'],
   ocaml: ['.ocaml-version', '.tool-versions', 'mise.toml'],
   fortran: ['.tool-versions', 'mise.toml'],
+  cuda: ['.cuda-version', 'environment.yml (cudatoolkit)', '.tool-versions', 'mise.toml'],
 };
 
 // Version filter options
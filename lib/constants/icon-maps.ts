@@ -54,6 +54,8 @@ export const PROVIDER_ICON_FILES: Record<string, string> = {
   vcpkg: "vcpkg",
   git: "git",
   fvm: "fvm",
+  juliaup: "julia",
+  rig: "r",
   pub: "pub",
   zig: "zig",
   luarocks: "luarocks",
@@ -11,6 +11,10 @@ export const CAPABILITY_COLORS: Record<string, string> = {
   lock_version: "bg-pink-100 text-pink-800 dark:bg-pink-900 dark:text-pink-300",
   rollback: "bg-indigo-100 text-indigo-800 dark:bg-indigo-900 dark:text-indigo-300",
   project_local: "bg-teal-100 text-teal-800 dark:bg-teal-900 dark:text-teal-300",
+  info: "bg-sky-100 text-sky-800 dark:bg-sky-900 dark:text-sky-300",
+  versions: "bg-violet-100 text-violet-800 dark:bg-violet-900 dark:text-violet-300",
+  requires_elevation: "bg-rose-100 text-rose-800 dark:bg-rose-900 dark:text-rose-300",
+  supports_prefix: "bg-emerald-100 text-emerald-800 dark:bg-emerald-900 dark:text-emerald-300",
 };
 
 export function getCapabilityColor(capability: string): string {
@@ -32,3 +36,11 @@ export function getCapabilityLabel(
   }
   return translated;
 }
+
+/** Whether a provider declared `capability`, so unsupported actions can be hidden */
+export function hasCapability(
+  capabilities: string[] | undefined,
+  capability: string,
+): boolean {
+  return capabilities?.includes(capability) ?? false;
+}
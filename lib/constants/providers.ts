@@ -82,7 +82,7 @@ export const ALL_PROVIDER_IDS = [
   'nvm', 'fnm', 'pyenv', 'rustup', 'goenv', 'rbenv', 'phpbrew',
   'sdkman', 'sdkman-kotlin', 'sdkman-scala', 'sdkman-groovy',
   'sdkman-gradle', 'sdkman-maven',
-  'volta', 'asdf', 'mise', 'nix', 'adoptium', 'fvm', 'zig',
+  'volta', 'asdf', 'mise', 'nix', 'adoptium', 'fvm', 'zig', 'juliaup', 'rig',
   // C/C++ package managers
   'vcpkg', 'conan', 'xmake',
   // Container / VCS / other
@@ -95,5 +95,5 @@ export const ALL_PROVIDER_IDS = [
   'system-elixir', 'system-erlang', 'system-swift', 'system-julia',
   'system-perl', 'system-r', 'system-haskell', 'system-clojure',
   'system-crystal', 'system-nim', 'system-ocaml', 'system-fortran',
-  'system-c', 'system-cpp',
+  'system-c', 'system-cpp', 'system-cuda',
 ] as const;
@@ -104,6 +104,8 @@ const SETTING_FOCUS_IDS: Record<string, string> = {
   "general.external_cache_excluded_providers": "external-cache-excluded-providers",
   "general.download_speed_limit": "download-speed-limit",
   "general.update_check_concurrency": "update-check-concurrency",
+  "general.metrics_listener_enabled": "metrics-listener-enabled",
+  "general.metrics_listener_port": "metrics-listener-port",
 
   // Network
   "network.timeout": "network-timeout",
@@ -414,6 +416,24 @@ const SETTINGS_REGISTRY_BASE: SettingDefinition[] = [
     keywords: ['update', 'check', 'concurrency', 'parallel', 'thread', '更新', '检查', '并发', '线程'],
     advanced: true,
   },
+  {
+    key: 'general.metrics_listener_enabled',
+    section: 'general',
+    labelKey: 'settings.metricsListenerEnabled',
+    descKey: 'settings.metricsListenerEnabledDesc',
+    type: 'switch',
+    keywords: ['metrics', 'prometheus', 'monitoring', 'http', '指标', '监控'],
+    advanced: true,
+  },
+  {
+    key: 'general.metrics_listener_port',
+    section: 'general',
+    labelKey: 'settings.metricsListenerPort',
+    descKey: 'settings.metricsListenerPortDesc',
+    type: 'input',
+    keywords: ['metrics', 'prometheus', 'port', '指标', '端口'],
+    advanced: true,
+  },
 
   // Network Settings
   {
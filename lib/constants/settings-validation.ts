@@ -41,7 +41,11 @@ export const VALIDATION_RULES: Record<string, ValidationRule> = {
   "general.cache_auto_clean_threshold": { min: 0, max: 100 },
   "general.cache_monitor_interval": { min: 0, max: 3600 },
   "general.download_speed_limit": { min: 0, max: 1073741824 },
+  "general.download_history_max_age_days": { min: 0, max: 3650 },
+  "general.download_history_max_records": { min: 10, max: 1000 },
+  "general.trash_retention_days": { min: 0, max: 3650 },
   "general.update_check_concurrency": { min: 1, max: 32 },
+  "general.metrics_listener_port": { min: 1024, max: 65535 },
   "startup.max_concurrent_scans": { min: 1, max: 16 },
   "startup.startup_timeout_secs": { min: 5, max: 120 },
   "backup.auto_backup_interval_hours": { min: 1, max: 720 },
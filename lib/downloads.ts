@@ -437,7 +437,7 @@ export function normalizeDownloadFailure(
     reason === "invalid_operation"
   ) {
     failureClass = "selection_error";
-  } else if (reason === "checksum_mismatch") {
+  } else if (reason === "checksum_mismatch" || reason === "verification_failed") {
     failureClass = "integrity_error";
   } else if (reason === "timeout") {
     failureClass = "timeout";
@@ -452,6 +452,7 @@ export function normalizeDownloadFailure(
     reason === "http_server_error" ||
     reason === "rate_limited" ||
     reason === "interrupted" ||
+    reason === "artifact_missing" ||
     reason === "filesystem_error" ||
     reason === "insufficient_space"
   ) {
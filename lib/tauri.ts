@@ -7,15 +7,29 @@ import type { ConflictInfo } from "@/types/tauri";
 // Re-export all types from types/tauri.ts
 export type {
   EnvInstallProgressEvent,
+  EnvInstallBatchRequest,
+  EnvInstallBatchProgress,
+  EnvInstallBatchResult,
   EnvVerifyResult,
+  EnvInstalledVersion,
+  SmokeTestDefinition,
+  SmokeTestReport,
+  SmokeTestResult,
   EnvVersionMutationResult,
+  PinFileFormat,
   EnvUpdateCheckResult,
   EnvCleanupResult,
+  InstallValidation,
   CleanedVersion,
   GlobalPackageInfo,
+  NodePackageManager,
+  NodePackageManagers,
   EnvMigrateResult,
+  EnvGlobalUpdateResult,
   MigrateFailure,
   EolCycleInfo,
+  EnvUpgradeAdvice,
+  EnvResolutionReport,
   EnvironmentInfo,
   CppCompilerMetadata,
   InstalledVersion,
@@ -33,16 +47,27 @@ export type {
   RustupScopedListResult,
   RustupProfileResult,
   RustupShowInfo,
+  RustToolchainSizeReport,
+  RustupGcOptions,
+  RustupGcResult,
+  VersionImportMode,
+  VersionImportReport,
+  VersionImportSelection,
   RustupOverride,
   GoEnvInfo,
   GoCacheInfo,
+  DotnetRuntime,
+  GoWorkInfo,
+  GoWorkModule,
   PackageSummary,
   PackageInfo,
   PackagePreflightSummary,
+  PackageTryInfo,
   VersionInfo,
   InstalledPackage,
   ProviderInfo,
   ProviderStatusInfo,
+  ProviderHealthMetric,
   CacheInfo,
   CacheStats,
   CacheAccessStats,
@@ -50,6 +75,9 @@ export type {
   CacheEntryList,
   CacheCommandScope,
   CacheVerificationResult,
+  CacheVerifyProgress,
+  CacheQuarantineEntry,
+  CacheQuarantinePurgeResult,
   CacheIssue,
   CacheRepairResult,
   CacheSettings,
@@ -62,6 +90,13 @@ export type {
   CleanedFileInfo,
   CleanupRecord,
   CleanupHistorySummary,
+  CleanupAnalyticsPeriod,
+  CleanupCategory,
+  WeeklyCleanup,
+  CategoryCleanup,
+  DirectoryCleanup,
+  CacheGrowthProjection,
+  CleanupAnalytics,
   BackupContentType,
   BackupManifest,
   BackupInfo,
@@ -74,14 +109,28 @@ export type {
   BackupExportResult,
   BackupImportResult,
   BackupCleanupResult,
+  StateComponent,
+  FullBackupOptions,
+  FullBackupProgress,
+  FullBackupResult,
+  FullBackupInspection,
+  FullRestoreResult,
   IntegrityCheckResult,
   DatabaseInfo,
   GpuInfo,
   PlatformInfo,
+  SettingsLoadReport,
+  ConfigImportPreview,
+  TeamConfigSection,
+  TeamConfigPreview,
+  TeamConfigStatus,
+  SettingsValidationIssue,
   DiskInfo,
   NetworkInterfaceInfo,
   SystemProxyInfo,
   ProxyTestResult,
+  EffectiveProxy,
+  ProxySource,
   ComponentInfo,
   BatteryInfo,
   BatchInstallOptions,
@@ -90,6 +139,7 @@ export type {
   BatchItemResult,
   BatchItemError,
   BatchItemSkipped,
+  BatchItemRollback,
   UpdateInfo,
   UpdateCheckProgress,
   UpdateCheckError,
@@ -97,6 +147,8 @@ export type {
   UpdateCheckCoverage,
   UpdateCheckSummary,
   SelfUpdateInfo,
+  SelfChannelVersion,
+  UpdateChannel,
   SelfUpdateSourceKind,
   SelfUpdateErrorCategory,
   SelfUpdateProgressEvent,
@@ -116,14 +168,25 @@ export type {
   ScoredPackage,
   SearchFacets,
   SearchSuggestion,
+  GlobalSearchKind,
+  GlobalSearchResponse,
   PackageComparison,
   PackageCompareItem,
   FeatureComparison,
   ManifestInfo,
+  ManifestDiagnostics,
+  ManifestDiagnostic,
+  ManifestDiagnosticSeverity,
+  ManifestTextRange,
+  ManifestFormat,
+  ManifestFormatResult,
   LogFileInfo,
   LogEntry,
   LogQueryOptions,
   LogQueryResult,
+  LogStats,
+  LogStatsOptions,
+  LogFollowBatch,
   LogExportOptions,
   LogExportResult,
   LogCleanupPolicyInput,
@@ -133,12 +196,24 @@ export type {
   CommandOutputEvent,
   DownloadProgress,
   DownloadTask,
+  DownloadQueueRestored,
+  DeepLinkAction,
+  DeepLinkRequest,
   DownloadQueueStats,
+  ExtractProgress,
+  LocalApiStatus,
   DownloadHistoryRecord,
   DownloadHistoryStats,
+  DownloadHistoryExportFormat,
+  DownloadHistoryFilter,
+  DownloadHistoryImportResult,
+  DownloadUsagePeriod,
+  DownloadUsageTotal,
+  DownloadUsageStats,
   DiskSpaceInfo,
   DownloadRequest,
   VerifyResult,
+  PartialValidation,
   DownloadShutdownOutcome,
   DownloadEvent,
   TrayIconState,
@@ -151,29 +226,52 @@ export type {
   TrayMenuConfig,
   TrayTerminalProfileEntry,
   TrayStateInfo,
+  NotificationCategory,
+  NotificationSeverity,
+  NotificationDelivery,
+  NotificationRecord,
+  NotificationFilter,
   ExtractionStrategy,
   VersionTransform,
   CustomDetectionRule,
   CustomDetectionResult,
+  CustomDetectionReport,
   TestRuleResult,
   RegexValidation,
   ImportRulesResult,
   ExtractionTypeInfo,
+  CommandTemplate,
+  VersionListSource,
+  CustomEnvTypeDefinition,
   HealthStatus,
   Severity,
   IssueCategory,
   HealthIssue,
   HealthScopeState,
   HealthRemediationResult,
+  JobEvent,
+  JobKind,
+  JobRecord,
+  JobStatus,
   EnvironmentHealthResult,
   PackageManagerHealthResult,
   SystemHealthResult,
   ProfileEnvironment,
   EnvironmentProfile,
   ProfileApplyResult,
+  ProfileImportEnvironmentStatus,
+  ProfileImportPreview,
   ProfileEnvironmentResult,
   ProfileEnvironmentError,
   ProfileEnvironmentSkipped,
+  Workspace,
+  WorkspaceRepo,
+  WorkspaceRepoStatus,
+  WorkspaceScanResult,
+  WorkspaceStatus,
+  WorkspaceToolchain,
+  WorkspaceToolchainError,
+  WorkspaceApplyResult,
   WslProfileSnapshot,
   WslProfileSnapshotDistro,
   WslProfileApplyResult,
@@ -189,6 +287,10 @@ export type {
   WslExecResult,
   WslDiskUsage,
   WslConfig,
+  WslConfigDiagnostic,
+  WslConfigKeyDoc,
+  WslConfigKind,
+  WslConfigSetKeyResult,
   WslDistroConfig,
   WslMountOptions,
   WslDistroEnvironment,
@@ -224,16 +326,41 @@ export type {
   GitRepoStats,
   GitBisectState,
   GitRebaseTodoItem,
+  GitRebasePlan,
+  GitRebaseExecuteOptions,
+  GitRebaseStatus,
   EditorCapabilityProbeResult,
   EditorOpenActionResult,
   LaunchRequest,
   LaunchResult,
+  LaunchLimitAction,
+  LaunchLimits,
+  TrackedLaunchInfo,
+  LaunchStats,
+  LaunchLimitEvent,
   ActivationScript,
   EnvInfoResult,
+  ProgramFileType,
+  ShimTarget,
+  ProgramMatch,
+  OnboardingSetupStep,
+  OnboardingSetupStepStatus,
+  OnboardingSetupStepState,
+  OnboardingSetupStatus,
+  PythonToolBackend,
+  PythonToolBackendChoice,
+  PythonTool,
+  PythonToolInstallResult,
+  PythonToolMigrateResult,
+  ProjectTask,
+  ProjectTaskSource,
   ShimInfo,
   PathStatusInfo,
+  InitShellType,
   EnvVarScope,
   EnvFileFormat,
+  EnvVarOperation,
+  EnvVarTransactionResult,
   EnvVarSupportSnapshot,
   EnvVarSummary,
   EnvVarRevealResult,
@@ -263,9 +390,13 @@ export type {
   ShellStartupMeasurement,
   ShellHealthResult,
   TerminalProfile,
+  TerminalLaunchRecord,
   TerminalEnvVarSummary,
   TerminalEnvVarRevealResult,
   TerminalProfileTemplate,
+  TerminalSessionInfo,
+  TerminalSessionOutputEvent,
+  TerminalSessionExitEvent,
   TerminalConfigDiagnostic,
   TerminalConfigEditorMetadata,
   TerminalConfigMutationResult,
@@ -273,10 +404,16 @@ export type {
   PSProfileInfo,
   PSModuleInfo,
   PSScriptInfo,
+  PSModuleUpdateResult,
+  PSModuleUpdateProgress,
+  PSModuleUninstallResult,
   ShellFrameworkInfo,
   ShellPlugin,
   FrameworkCacheInfo,
   ShellConfigEntries,
+  ShellConfigEntryKind,
+  ShellProfileAnalysis,
+  ShellProfileFixResult,
   DiagnosticExportOptions,
   DiagnosticCaptureFrontendCrashOptions,
   DiagnosticExportResult,
@@ -298,16 +435,28 @@ export type {
 
 import type {
   EnvInstallProgressEvent,
+  EnvInstallBatchRequest,
+  EnvInstallBatchProgress,
+  EnvInstallBatchResult,
   EnvVerifyResult,
+  EnvInstalledVersion,
   EnvUpdateCheckResult,
+  ReleaseNotes,
   EnvCleanupResult,
+  InstallValidation,
   GlobalPackageInfo,
+  NodePackageManager,
+  NodePackageManagers,
   EnvMigrateResult,
+  EnvGlobalUpdateResult,
   EolCycleInfo,
+  EnvUpgradeAdvice,
+  EnvResolutionReport,
   EnvironmentInfo,
   InstalledVersion,
   DetectedEnvironment,
   EnvVersionMutationResult,
+  PinFileFormat,
   EnvironmentProviderInfo,
   EnvironmentSettingsConfig,
   SystemEnvironmentInfo,
@@ -319,12 +468,22 @@ import type {
   RustupScopedListResult,
   RustupProfileResult,
   RustupShowInfo,
+  RustToolchainSizeReport,
+  RustupGcOptions,
+  RustupGcResult,
+  VersionImportMode,
+  VersionImportReport,
+  VersionImportSelection,
   RustupOverride,
   GoEnvInfo,
   GoCacheInfo,
+  DotnetRuntime,
+  GoWorkInfo,
+  GoWorkModule,
   PackageSummary,
   PackageInfo,
   PackagePreflightSummary,
+  PackageTryInfo,
   VersionInfo,
   InstalledPackage,
   ProviderInfo,
@@ -334,6 +493,8 @@ import type {
   CacheEntryItem,
   CacheEntryList,
   CacheVerificationResult,
+  CacheQuarantineEntry,
+  CacheQuarantinePurgeResult,
   CacheRepairResult,
   CacheCommandScope,
   CacheSettings,
@@ -344,6 +505,13 @@ import type {
   EnhancedCleanResult,
   CleanupRecord,
   CleanupHistorySummary,
+  CleanupAnalyticsPeriod,
+  CleanupCategory,
+  WeeklyCleanup,
+  CategoryCleanup,
+  DirectoryCleanup,
+  CacheGrowthProjection,
+  CleanupAnalytics,
   BackupContentType,
   BackupInfo,
   BackupResult,
@@ -353,13 +521,26 @@ import type {
   BackupExportResult,
   BackupImportResult,
   BackupCleanupResult,
+  StateComponent,
+  FullBackupOptions,
+  FullBackupProgress,
+  FullBackupResult,
+  FullBackupInspection,
+  FullRestoreResult,
   IntegrityCheckResult,
   DatabaseInfo,
   PlatformInfo,
+  SettingsLoadReport,
+  ConfigImportPreview,
+  TeamConfigSection,
+  TeamConfigPreview,
+  TeamConfigStatus,
   DiskInfo,
   NetworkInterfaceInfo,
   SystemProxyInfo,
   ProxyTestResult,
+  EffectiveProxy,
+  ProxySource,
   ComponentInfo,
   BatteryInfo,
   BatchProgress,
@@ -368,6 +549,8 @@ import type {
   UpdateCheckProgress,
   UpdateCheckSummary,
   SelfUpdateInfo,
+  SelfChannelVersion,
+  UpdateChannel,
   SelfUpdateProgressEvent,
   InstallHistoryEntry,
   InstallHistoryQuery,
@@ -379,11 +562,18 @@ import type {
   SearchFilters,
   EnhancedSearchResult,
   SearchSuggestion,
+  GlobalSearchKind,
+  GlobalSearchResponse,
   PackageComparison,
   ManifestInfo,
+  ManifestDiagnostics,
+  ManifestFormatResult,
   LogFileInfo,
   LogQueryOptions,
   LogQueryResult,
+  LogStats,
+  LogStatsOptions,
+  LogFollowBatch,
   LogExportOptions,
   LogExportResult,
   LogCleanupPolicyInput,
@@ -393,12 +583,23 @@ import type {
   CommandOutputEvent,
   DownloadProgress,
   DownloadTask,
+  DownloadQueueRestored,
+  DeepLinkRequest,
   DownloadQueueStats,
+  ExtractProgress,
+  LocalApiStatus,
   DownloadHistoryRecord,
   DownloadHistoryStats,
+  DownloadHistoryExportFormat,
+  DownloadHistoryFilter,
+  DownloadHistoryImportResult,
+  DownloadUsagePeriod,
+  DownloadUsageTotal,
+  DownloadUsageStats,
   DiskSpaceInfo,
   DownloadRequest,
   VerifyResult,
+  PartialValidation,
   DownloadShutdownOutcome,
   TrayIconState,
   TrayLanguage,
@@ -410,19 +611,35 @@ import type {
   TrayMenuConfig,
   TrayTerminalProfileEntry,
   TrayStateInfo,
+  NotificationRecord,
+  NotificationFilter,
   CustomDetectionRule,
   CustomDetectionResult,
+  CustomDetectionReport,
   TestRuleResult,
   RegexValidation,
   ImportRulesResult,
   ExtractionTypeInfo,
+  CommandTemplate,
+  VersionListSource,
+  CustomEnvTypeDefinition,
   HealthRemediationResult,
+  JobEvent,
+  JobKind,
+  JobRecord,
+  JobStatus,
   SystemHealthResult,
   EnvironmentHealthResult,
   PackageManagerHealthResult,
   EnvironmentProfile,
   ProfileEnvironment,
   ProfileApplyResult,
+  ProfileImportEnvironmentStatus,
+  ProfileImportPreview,
+  Workspace,
+  WorkspaceScanResult,
+  WorkspaceStatus,
+  WorkspaceApplyResult,
   WslProfileSnapshot,
   WslProfileApplyResult,
   WslPortForwardRule,
@@ -436,12 +653,18 @@ import type {
   WslExecResult,
   WslDiskUsage,
   WslConfig,
+  WslConfigDiagnostic,
+  WslConfigKeyDoc,
+  WslConfigKind,
+  WslConfigSetKeyResult,
   WslDistroConfig,
   WslMountOptions,
   WslDistroEnvironment,
   WslDistroResources,
   WslUser,
   WslPackageUpdateResult,
+  WslBatchItemResult,
+  WslBatchProgress,
   WslExportWindowsEnvResult,
   WslDistroEnvReadResult,
   WslEnvEntry,
@@ -474,16 +697,38 @@ import type {
   GitRepoStats,
   GitBisectState,
   GitRebaseTodoItem,
+  GitRebasePlan,
+  GitRebaseExecuteOptions,
+  GitRebaseStatus,
   EditorCapabilityProbeResult,
   EditorOpenActionResult,
   LaunchRequest,
   LaunchResult,
+  TrackedLaunchInfo,
+  LaunchStats,
+  LaunchLimitEvent,
   ActivationScript,
   EnvInfoResult,
+  ProgramFileType,
+  ShimTarget,
+  ProgramMatch,
+  OnboardingSetupStep,
+  OnboardingSetupStepStatus,
+  OnboardingSetupStepState,
+  OnboardingSetupStatus,
+  PythonToolBackend,
+  PythonToolBackendChoice,
+  PythonTool,
+  PythonToolInstallResult,
+  PythonToolMigrateResult,
+  ProjectTask,
   ShimInfo,
   PathStatusInfo,
+  InitShellType,
   EnvVarScope,
   EnvFileFormat,
+  EnvVarOperation,
+  EnvVarTransactionResult,
   EnvVarSupportSnapshot,
   EnvVarSummary,
   EnvVarRevealResult,
@@ -512,9 +757,13 @@ import type {
   ShellStartupMeasurement,
   ShellHealthResult,
   TerminalProfile,
+  TerminalLaunchRecord,
   TerminalEnvVarSummary,
   TerminalEnvVarRevealResult,
   TerminalProfileTemplate,
+  TerminalSessionInfo,
+  TerminalSessionOutputEvent,
+  TerminalSessionExitEvent,
   TerminalConfigDiagnostic,
   TerminalConfigEditorMetadata,
   TerminalConfigMutationResult,
@@ -522,10 +771,16 @@ import type {
   PSProfileInfo,
   PSModuleInfo,
   PSScriptInfo,
+  PSModuleUpdateResult,
+  PSModuleUpdateProgress,
+  PSModuleUninstallResult,
   ShellFrameworkInfo,
   ShellPlugin,
   FrameworkCacheInfo,
   ShellConfigEntries,
+  ShellConfigEntryKind,
+  ShellProfileAnalysis,
+  ShellProfileFixResult,
   DiagnosticExportOptions,
   DiagnosticCaptureFrontendCrashOptions,
   DiagnosticExportResult,
@@ -618,6 +873,22 @@ export async function listenEnvInstallProgress(
   });
 }
 
+// Install several environments concurrently
+export const envInstallBatch = (requests: EnvInstallBatchRequest[]) =>
+  invoke<EnvInstallBatchResult>("env_install_batch", { requests });
+
+// Listen for aggregated batch install progress
+export async function listenEnvInstallBatchProgress(
+  callback: (progress: EnvInstallBatchProgress) => void,
+): Promise<UnlistenFn> {
+  return listen<EnvInstallBatchProgress>(
+    "env-install-batch-progress",
+    (event) => {
+      callback(event.payload);
+    },
+  );
+}
+
 // Cancel an ongoing environment installation
 export const envInstallCancel = (envType: string, version: string) =>
   invoke<boolean>("env_install_cancel", { envType, version });
@@ -640,7 +911,14 @@ export const envUninstall = (
   envType: string,
   version: string,
   providerId?: string,
-) => invoke<void>("env_uninstall", { envType, version, providerId });
+  removeForeignFiles?: boolean,
+) =>
+  invoke<void>("env_uninstall", {
+    envType,
+    version,
+    providerId,
+    removeForeignFiles,
+  });
 export const envUseGlobal = (
   envType: string,
   version: string,
@@ -656,12 +934,24 @@ export const envUseLocal = (
   version: string,
   projectPath: string,
   providerId?: string,
+  pinFileFormat?: PinFileFormat,
 ) =>
   invoke<EnvVersionMutationResult>("env_use_local", {
     envType,
     version,
     projectPath,
     providerId,
+    pinFileFormat,
+  });
+export const importFromVersionManagers = (
+  mode?: VersionImportMode,
+  selections?: VersionImportSelection[],
+  removeOriginals?: boolean,
+) =>
+  invoke<VersionImportReport>("import_from_version_managers", {
+    mode,
+    selections,
+    removeOriginals,
   });
 export const envDetect = (envType: string, startPath: string) =>
   invoke<DetectedEnvironment | null>("env_detect", { envType, startPath });
@@ -672,7 +962,18 @@ export const envAvailableVersions = (
   providerId?: string,
   force?: boolean,
 ) =>
-  invoke<VersionInfo[]>("env_available_versions", {
+  invoke<import("@/types/tauri").AvailableVersions>("env_available_versions", {
+    envType,
+    providerId,
+    force,
+  });
+/** Sync available versions with upstream; `force` refetches the full list */
+export const envRefreshVersions = (
+  envType: string,
+  force: boolean,
+  providerId?: string,
+) =>
+  invoke<import("@/types/tauri").VersionSyncResult>("env_refresh_versions", {
     envType,
     providerId,
     force,
@@ -719,13 +1020,16 @@ export const envVerifyInstall = (
     version,
     providerId,
   });
+/** Approve the current command line of `general.smoke_tests[index]` */
+export const envSmokeTestApprove = (index: number) =>
+  invoke<void>("env_smoke_test_approve", { index });
 
 export const envInstalledVersions = (
   envType: string,
   providerId?: string,
   force?: boolean,
 ) =>
-  invoke<InstalledVersion[]>("env_installed_versions", {
+  invoke<EnvInstalledVersion[]>("env_installed_versions", {
     envType,
     providerId,
     force,
@@ -741,6 +1045,20 @@ export const envCheckUpdates = (envType: string) =>
 export const envCheckUpdatesAll = () =>
   invoke<EnvUpdateCheckResult[]>("env_check_updates_all");
 
+/** Release notes between two versions of an environment or a package */
+export const releaseNotesGet = (
+  target: { envType: string } | { provider: string; package: string },
+  fromVersion: string,
+  toVersion: string,
+) =>
+  invoke<ReleaseNotes>("release_notes_get", {
+    envType: "envType" in target ? target.envType : null,
+    provider: "provider" in target ? target.provider : null,
+    package: "package" in target ? target.package : null,
+    fromVersion,
+    toVersion,
+  });
+
 export const envCleanupVersions = (
   envType: string,
   versionsToRemove: string[],
@@ -750,23 +1068,36 @@ export const envCleanupVersions = (
     versionsToRemove,
   });
 
+/** Check installed versions for partial or broken installs */
+export const envValidateInstalls = (envType?: string) =>
+  invoke<InstallValidation[]>("env_validate_installs", { envType });
+
+/** Remove conclusively broken installs along with their shims and current links; doubtful ones are skipped */
+export const envRemoveBroken = (envType: string, versions: string[]) =>
+  invoke<EnvCleanupResult>("env_remove_broken", { envType, versions });
+
+/** Global packages of an env version; Node lists every installed package manager unless `packageManager` picks one */
 export const envListGlobalPackages = (
   envType: string,
   version: string,
   providerId?: string,
+  packageManager?: NodePackageManager,
 ) =>
   invoke<GlobalPackageInfo[]>("env_list_global_packages", {
     envType,
     version,
     providerId,
+    packageManager,
   });
 
+/** Node globals are reinstalled with the manager that installed them unless `packageManager` overrides it */
 export const envMigratePackages = (
   envType: string,
   fromVersion: string,
   toVersion: string,
   packages: string[],
   providerId?: string,
+  packageManager?: NodePackageManager,
 ) =>
   invoke<EnvMigrateResult>("env_migrate_packages", {
     envType,
@@ -774,6 +1105,34 @@ export const envMigratePackages = (
     toVersion,
     packages,
     providerId,
+    packageManager,
+  });
+
+export const envUpdateGlobalPackages = (
+  envType: string,
+  version: string,
+  packages: string[],
+  providerId?: string,
+  packageManager?: NodePackageManager,
+) =>
+  invoke<EnvGlobalUpdateResult>("env_update_global_packages", {
+    envType,
+    version,
+    packages,
+    providerId,
+    packageManager,
+  });
+
+/** Package managers usable with a Node version, and the default pick for `projectPath` */
+export const envNodePackageManagers = (
+  version: string,
+  providerId?: string,
+  projectPath?: string,
+) =>
+  invoke<NodePackageManagers>("env_node_package_managers", {
+    version,
+    providerId,
+    projectPath,
   });
 
 export async function listenEnvMigrateProgress(
@@ -801,6 +1160,22 @@ export const envGetEolInfo = (envType: string) =>
 export const envGetVersionEol = (envType: string, version: string) =>
   invoke<EolCycleInfo | null>("env_get_version_eol", { envType, version });
 
+/** EOL status and upgrade recommendations for installed versions */
+export const envUpgradeAdvice = (
+  envType: string,
+  warningDays?: number,
+  projectDirs?: string[],
+) =>
+  invoke<EnvUpgradeAdvice>("env_upgrade_advice", {
+    envType,
+    warningDays,
+    projectDirs,
+  });
+
+/** Explain which PATH entry, shim, pin or version manager decides the active version */
+export const envExplainResolution = (envType: string, cwd: string) =>
+  invoke<EnvResolutionReport>("env_explain_resolution", { envType, cwd });
+
 // Rustup-specific commands
 export const rustupListComponents = (toolchain?: string) =>
   invoke<RustupScopedListResult<RustComponent>>("rustup_list_components", {
@@ -862,32 +1237,79 @@ export const rustupGetProfile = () =>
 export const rustupSetProfile = (profile: string) =>
   invoke<RustupOperationResult>("rustup_set_profile", { profile });
 
+export const rustupToolchainSizes = () =>
+  invoke<RustToolchainSizeReport>("rustup_toolchain_sizes");
+
+export const rustupGc = (options: RustupGcOptions) =>
+  invoke<RustupGcResult>("rustup_gc", { options });
+
 // Go-specific commands
 export const goEnvInfo = () => invoke<GoEnvInfo>("go_env_info");
 
-export const goModTidy = (projectPath: string) =>
-  invoke<string>("go_mod_tidy", { projectPath });
+export const goModTidy = (projectPath: string, module?: string) =>
+  invoke<string>("go_mod_tidy", { projectPath, module });
+
+export const goModDownload = (projectPath: string, module?: string) =>
+  invoke<string>("go_mod_download", { projectPath, module });
+
+export const goWorkInfo = (path: string) =>
+  invoke<GoWorkInfo>("go_work_info", { path });
 
-export const goModDownload = (projectPath: string) =>
-  invoke<string>("go_mod_download", { projectPath });
+export const goWorkSync = (path: string) =>
+  invoke<string>("go_work_sync", { path });
 
 export const goCleanCache = (cacheType: string) =>
   invoke<string>("go_clean_cache", { cacheType });
 
 export const goCacheInfo = () => invoke<GoCacheInfo>("go_cache_info");
+export const dotnetListRuntimes = () =>
+  invoke<DotnetRuntime[]>("dotnet_list_runtimes");
 
 // Package commands
 export const packageSearch = (
   query: string,
   provider?: string,
   force?: boolean,
-) => invoke<PackageSummary[]>("package_search", { query, provider, force });
+) =>
+  invoke<import("@/types/tauri").CachedResponse<PackageSummary[]>>(
+    "package_search",
+    { query, provider, force },
+  );
 export const packageInfo = (name: string, provider?: string, force?: boolean) =>
-  invoke<PackageInfo>("package_info", { name, provider, force });
+  invoke<import("@/types/tauri").CachedResponse<PackageInfo>>("package_info", {
+    name,
+    provider,
+    force,
+  });
+/** Pre-populate the search/info cache so the packages stay available offline */
+export const providerCacheWarm = (provider: string, queries: string[]) =>
+  invoke<import("@/types/tauri").CacheWarmResult>("provider_cache_warm", {
+    provider,
+    queries,
+  });
+export const providerSelftest = (provider: string) =>
+  invoke<import("@/types/tauri").ProviderSelftestReport>("provider_selftest", {
+    provider,
+  });
 export const preInstallValidate = (packages: string[]) =>
   invoke<PackagePreflightSummary>("pre_install_validate", { packages });
 export const packageInstall = (packages: string[]) =>
   invoke<string[]>("package_install", { packages });
+/** Install a package into a throwaway prefix that expires after `ttlHours` */
+export const packageTry = (
+  provider: string,
+  name: string,
+  version?: string,
+  ttlHours?: number,
+) =>
+  invoke<PackageTryInfo>("package_try", { provider, name, version, ttlHours });
+export const packageTryList = () =>
+  invoke<PackageTryInfo[]>("package_try_list");
+/** Install a tried package into the real environment and drop its prefix */
+export const packageTryPromote = (id: string) =>
+  invoke<string[]>("package_try_promote", { id });
+export const packageTryRemove = (id: string) =>
+  invoke<boolean>("package_try_remove", { id });
 export const packageUninstall = (packages: string[]) =>
   invoke<void>("package_uninstall", { packages });
 export const packageList = (provider?: string, force?: boolean) =>
@@ -917,6 +1339,22 @@ export interface AppInitStatus {
 
 export const appCheckInit = () => invoke<AppInitStatus>("app_check_init");
 
+// Onboarding
+export const onboardingStatus = () =>
+  invoke<OnboardingSetupStatus>("onboarding_status");
+export const onboardingCompleteStep = (
+  step: OnboardingSetupStep,
+  payload?: Record<string, unknown>,
+) =>
+  invoke<OnboardingSetupStatus>("onboarding_complete_step", {
+    step,
+    payload,
+  });
+export const onboardingSkipStep = (step: OnboardingSetupStep) =>
+  invoke<OnboardingSetupStatus>("onboarding_skip_step", { step });
+export const onboardingReset = () =>
+  invoke<OnboardingSetupStatus>("onboarding_reset");
+
 // Init progress events
 export interface InitProgressEvent {
   phase: string;
@@ -945,8 +1383,22 @@ export const configListDefaults = () =>
   invoke<[string, string][]>("config_list_defaults");
 export const configReset = () => invoke<void>("config_reset");
 export const configExport = () => invoke<string>("config_export");
-export const configImport = (tomlContent: string) =>
-  invoke<void>("config_import", { tomlContent });
+export const configImportPreview = (tomlContent: string) =>
+  invoke<ConfigImportPreview>("config_import_preview", { tomlContent });
+export const configImport = (tomlContent: string, keys?: string[]) =>
+  invoke<SettingsLoadReport>("config_import", { tomlContent, keys });
+export const configTakeLoadReport = () =>
+  invoke<SettingsLoadReport | null>("config_take_load_report");
+export const teamConfigExport = (
+  keys: TeamConfigSection[],
+  passphrase: string,
+) => invoke<string>("team_config_export", { keys, passphrase });
+export const teamConfigPreview = (bundle: string, passphrase: string) =>
+  invoke<TeamConfigPreview>("team_config_preview", { bundle, passphrase });
+export const teamConfigApply = (bundle: string, passphrase: string) =>
+  invoke<TeamConfigPreview>("team_config_apply", { bundle, passphrase });
+export const teamConfigStatus = () =>
+  invoke<TeamConfigStatus>("team_config_status");
 export const getCogniaDir = () => invoke<string>("get_cognia_dir");
 export const getPlatformInfo = () => invoke<PlatformInfo>("get_platform_info");
 export const getDiskInfo = () => invoke<DiskInfo[]>("get_disk_info");
@@ -954,8 +1406,16 @@ export const getNetworkInterfaces = () =>
   invoke<NetworkInterfaceInfo[]>("get_network_interfaces");
 export const detectSystemProxy = () =>
   invoke<SystemProxyInfo>("detect_system_proxy");
-export const testProxyConnection = (proxyUrl: string, testUrl?: string) =>
-  invoke<ProxyTestResult>("test_proxy_connection", { proxyUrl, testUrl });
+export const testProxyConnection = (
+  proxyUrl: string | null,
+  testUrl?: string,
+  provider?: string,
+) =>
+  invoke<ProxyTestResult>("test_proxy_connection", {
+    proxyUrl,
+    testUrl: testUrl ?? null,
+    provider: provider ?? null,
+  });
 export const getComponentsInfo = () =>
   invoke<ComponentInfo[]>("get_components_info");
 export const getBatteryInfo = () =>
@@ -970,6 +1430,7 @@ export interface SecretVaultStatus {
 export interface ProviderSecretStatus {
   provider: string;
   configured: boolean;
+  configuredInStore: boolean;
   configuredInVault: boolean;
   configuredInEnv: boolean;
   needsUnlock: boolean;
@@ -987,6 +1448,31 @@ export const secretVaultLock = () =>
 export const secretVaultReset = () =>
   invoke<SecretVaultStatus>("secret_vault_reset");
 
+export type SecretBackend = "keychain" | "encrypted_file";
+
+export interface SecretStoreStatus {
+  backend: SecretBackend;
+}
+
+/** A stored secret, without its value */
+export interface SecretRef {
+  namespace: string;
+  key: string;
+  backend: SecretBackend;
+  updatedAt: string;
+}
+
+export const secretStoreStatus = () =>
+  invoke<SecretStoreStatus>("secret_store_status");
+export const secretSet = (namespace: string, key: string, value: string) =>
+  invoke<SecretRef>("secret_set", { namespace, key, value });
+export const secretGet = (namespace: string, key: string) =>
+  invoke<string | null>("secret_get", { namespace, key });
+export const secretDelete = (namespace: string, key: string) =>
+  invoke<boolean>("secret_delete", { namespace, key });
+export const secretList = (namespace: string) =>
+  invoke<SecretRef[]>("secret_list", { namespace });
+
 // Cache commands
 export const cacheInfo = () => invoke<CacheInfo>("cache_info");
 export interface CacheCleanResult {
@@ -1010,8 +1496,18 @@ export const cacheCleanPreview = (cleanType?: string) =>
   invoke<CleanPreview>("cache_clean_preview", { cleanType });
 export const cacheCleanEnhanced = (cleanType?: string, useTrash?: boolean) =>
   invoke<EnhancedCleanResult>("cache_clean_enhanced", { cleanType, useTrash });
-export const cacheVerify = (scope: CacheCommandScope = "all") =>
-  invoke<CacheVerificationResult>("cache_verify", { scope });
+/** Hash-verify the cache and quarantine corrupted downloads. Tracked as a cancellable job. */
+export const cacheVerify = (
+  scope: CacheCommandScope = "all",
+  concurrency?: number,
+) => invoke<CacheVerificationResult>("cache_verify", { scope, concurrency });
+export const cacheQuarantineList = () =>
+  invoke<CacheQuarantineEntry[]>("cache_quarantine_list");
+export const cacheQuarantineRestore = (id: string) =>
+  invoke<CacheQuarantineEntry>("cache_quarantine_restore", { id });
+/** Permanently delete quarantined files; all of them when `ids` is omitted */
+export const cacheQuarantinePurge = (ids?: string[]) =>
+  invoke<CacheQuarantinePurgeResult>("cache_quarantine_purge", { ids });
 export const cacheRepair = (scope: CacheCommandScope = "all") =>
   invoke<CacheRepairResult>("cache_repair", { scope });
 export const getCacheSettings = () =>
@@ -1024,6 +1520,8 @@ export const clearCleanupHistory = () =>
   invoke<number>("clear_cleanup_history");
 export const getCleanupSummary = () =>
   invoke<CleanupHistorySummary>("get_cleanup_summary");
+export const cleanupAnalytics = (period?: CleanupAnalyticsPeriod) =>
+  invoke<CleanupAnalytics>("cleanup_analytics", { period });
 
 // Cache access stats
 export const getCacheAccessStats = () =>
@@ -1031,6 +1529,13 @@ export const getCacheAccessStats = () =>
 export const resetCacheAccessStats = () =>
   invoke<void>("reset_cache_access_stats");
 
+// Prometheus text-format metrics
+export const metricsExport = () => invoke<string>("metrics_export");
+
+// Read-only local JSON-RPC API
+export const apiServerStatus = () =>
+  invoke<LocalApiStatus>("api_server_status");
+
 // Cache entry browser
 export const listCacheEntries = (options?: {
   entryType?: string;
@@ -1269,6 +1774,8 @@ export interface MigrationValidation {
   destinationSpaceHuman: string;
   hasEnoughSpace: boolean;
   isSameDrive: boolean;
+  isNetworkDrive: boolean;
+  resumableFiles: number;
   errors: string[];
   warnings: string[];
 }
@@ -1281,27 +1788,108 @@ export interface MigrationResult {
   bytesMigrated: number;
   bytesMigratedHuman: string;
   filesCount: number;
+  filesSkipped: number;
   symlinkCreated: boolean;
+  sourceKept: boolean;
+  resumable: boolean;
   error: string | null;
 }
 
+export type MigrationStage =
+  | "validating"
+  | "calculating"
+  | "copying"
+  | "verifying"
+  | "creating_link"
+  | "cleaning_up"
+  | "updating_config"
+  | "complete"
+  | "failed";
+
+export interface MigrationProgress {
+  stage: MigrationStage;
+  message: string;
+  bytesCopied: number;
+  totalBytes: number;
+  filesCopied: number;
+  filesSkipped: number;
+  totalFiles: number;
+  currentFile: string | null;
+  percent: number;
+}
+
+export interface CacheMigrateOptions {
+  keepSource?: boolean;
+  verifyHashes?: boolean;
+}
+
 export const cacheMigrationValidate = (destination: string) =>
   invoke<MigrationValidation>("cache_migration_validate", { destination });
 export const cacheMigrate = (
   destination: string,
   mode: "move" | "move_and_link",
-) => invoke<MigrationResult>("cache_migrate", { destination, mode });
+  options?: CacheMigrateOptions,
+) =>
+  invoke<MigrationResult>("cache_migrate", {
+    destination,
+    mode,
+    keepSource: options?.keepSource ?? null,
+    verifyHashes: options?.verifyHashes ?? null,
+  });
+
+export async function listenCacheMigrationProgress(
+  callback: (progress: MigrationProgress) => void,
+): Promise<UnlistenFn> {
+  return listen<MigrationProgress>("cache-migration-progress", (event) => {
+    callback(event.payload);
+  });
+}
 
 // Force clean
 export const cacheForceClean = (useTrash?: boolean) =>
   invoke<EnhancedCleanResult>("cache_force_clean", { useTrash });
+export type RegenerationCost = "cheap" | "expensive";
+
+export interface ExternalCleanPathItem {
+  path: string;
+  size: number;
+  sizeHuman: string;
+  cost: RegenerationCost;
+}
+
+export interface ExternalCleanModeInfo {
+  id: string;
+  description: string;
+  usesCommand: boolean;
+}
+
+export interface ExternalCleanPreview {
+  provider: string;
+  displayName: string;
+  cachePath: string | null;
+  totalSize: number;
+  totalSizeHuman: string;
+  paths: ExternalCleanPathItem[];
+  cost: RegenerationCost;
+  regenerates: string;
+  modes: ExternalCleanModeInfo[];
+  token: string;
+  tokenExpiresAt: string;
+}
+
+export const cleanExternalCachePreview = (provider: string) =>
+  invoke<ExternalCleanPreview>("clean_external_cache_preview", { provider });
 export const cacheForceCleanExternal = (
   provider: string,
+  previewToken: string,
+  mode?: string,
   useCommand?: boolean,
   useTrash?: boolean,
 ) =>
   invoke<ExternalCacheCleanResult>("cache_force_clean_external", {
     provider,
+    previewToken,
+    mode: mode ?? null,
     useCommand,
     useTrash,
   });
@@ -1404,6 +1992,23 @@ export const backupImport = (zipPath: string) =>
   invoke<BackupImportResult>("backup_import", { zipPath });
 export const backupCleanup = (maxCount: number, maxAgeDays: number) =>
   invoke<BackupCleanupResult>("backup_cleanup", { maxCount, maxAgeDays });
+
+/** Archive the whole Cognia state directory; the download cache is opt-in */
+export const backupFullCreate = (options: FullBackupOptions = {}) =>
+  invoke<FullBackupResult>("backup_full_create", { options });
+export const backupInspect = (path: string) =>
+  invoke<FullBackupInspection>("backup_inspect", { path });
+/** Restore the given components, or every component in the archive when empty */
+export const backupFullRestore = (path: string, components: StateComponent[] = []) =>
+  invoke<FullRestoreResult>("backup_full_restore", { path, components });
+
+export async function listenBackupFullProgress(
+  callback: (progress: FullBackupProgress) => void,
+): Promise<UnlistenFn> {
+  return listen<FullBackupProgress>("backup-full-progress", (event) => {
+    callback(event.payload);
+  });
+}
 export const dbIntegrityCheck = () =>
   invoke<IntegrityCheckResult>("db_integrity_check");
 export const dbGetInfo = () => invoke<DatabaseInfo>("db_get_info");
@@ -1473,8 +2078,14 @@ export const batchInstall = (
 export const batchUninstall = (packages: string[], force?: boolean) =>
   invoke<BatchResult>("batch_uninstall", { packages, force });
 
-export const batchUpdate = (packages?: string[]) =>
-  invoke<BatchResult>("batch_update", { packages });
+export const batchUpdate = (
+  packages?: string[],
+  verifyAfterUpdate?: boolean,
+) =>
+  invoke<BatchResult>("batch_update", {
+    packages,
+    verifyAfterUpdate: verifyAfterUpdate ?? null,
+  });
 
 // Update checking commands
 export const checkUpdates = (packages?: string[], concurrency?: number) =>
@@ -1590,6 +2201,27 @@ export const advancedSearch = (options: AdvancedSearchOptions) =>
 export const searchSuggestions = (query: string, limit?: number) =>
   invoke<SearchSuggestion[]>("search_suggestions", { query, limit });
 
+/** Command-palette search across packages, environments, downloads and settings */
+export const globalSearch = (
+  query: string,
+  limit?: number,
+  kinds?: GlobalSearchKind[],
+  requestId?: string,
+) =>
+  invoke<GlobalSearchResponse>("global_search", {
+    query,
+    limit: limit ?? null,
+    kinds: kinds ?? null,
+    requestId: requestId ?? null,
+  });
+
+/** Cancel some or all sources of an in-flight global search */
+export const globalSearchCancel = (
+  requestId: string,
+  kinds?: GlobalSearchKind[],
+) =>
+  invoke<number>("global_search_cancel", { requestId, kinds: kinds ?? null });
+
 // Package comparison
 export const comparePackages = (packages: [string, string | null][]) =>
   invoke<PackageComparison>("compare_packages", { packages });
@@ -1597,7 +2229,29 @@ export const comparePackages = (packages: [string, string | null][]) =>
 // Self update
 export const selfCheckUpdate = () =>
   invoke<SelfUpdateInfo>("self_check_update");
-export const selfUpdate = () => invoke<void>("self_update");
+/** Install the channel's update; moving to an older build needs `allowDowngrade` */
+export const selfUpdate = (allowDowngrade?: boolean) =>
+  invoke<void>("self_update", { allowDowngrade });
+/** Latest build per channel (or for one channel) for the channel switcher */
+export const selfListAvailableVersions = (channel?: UpdateChannel) =>
+  invoke<SelfChannelVersion[]>("self_list_available_versions", { channel });
+
+// Scheduled background update checks
+export const scheduleUpdateCheckEnable = (
+  interval: import("@/types/tauri").ScheduleInterval,
+) =>
+  invoke<import("@/types/tauri").ScheduleStatus>(
+    "schedule_update_check_enable",
+    { interval },
+  );
+export const scheduleUpdateCheckDisable = () =>
+  invoke<boolean>("schedule_update_check_disable");
+export const scheduleUpdateCheckStatus = () =>
+  invoke<import("@/types/tauri").ScheduleStatus>("schedule_update_check_status");
+export const scheduleUpdateCheckLastReport = () =>
+  invoke<import("@/types/tauri").BackgroundUpdateReport | null>(
+    "schedule_update_check_last_report",
+  );
 
 export async function listenSelfUpdateProgress(
   callback: (progress: SelfUpdateProgressEvent) => void,
@@ -1612,11 +2266,28 @@ export const manifestRead = (projectPath?: string) =>
   invoke<ManifestInfo | null>("manifest_read", { projectPath });
 export const manifestInit = (projectPath?: string) =>
   invoke<void>("manifest_init", { projectPath });
+export const manifestDiagnostics = (path: string) =>
+  invoke<ManifestDiagnostics>("manifest_diagnostics", { path });
+export const manifestFormat = (path: string) =>
+  invoke<ManifestFormatResult>("manifest_format", { path });
 
 // Log operations
 export const logListFiles = () => invoke<LogFileInfo[]>("log_list_files");
 export const logQuery = (options: LogQueryOptions) =>
   invoke<LogQueryResult>("log_query", { options });
+export const logStats = (options: LogStatsOptions) =>
+  invoke<LogStats>("log_stats", { options });
+export const logFollowStart = (options: LogQueryOptions) =>
+  invoke<string>("log_follow_start", { options });
+export const logFollowStop = (followId: string) =>
+  invoke<boolean>("log_follow_stop", { followId });
+export async function listenLogFollowEntries(
+  callback: (batch: LogFollowBatch) => void,
+): Promise<UnlistenFn> {
+  return listen<LogFollowBatch>("log-follow-entries", (event) => {
+    callback(event.payload);
+  });
+}
 export const logClear = (fileName?: string) =>
   invoke<LogCleanupResult>("log_clear", { fileName });
 export const logGetDir = () => invoke<string>("log_get_dir");
@@ -1706,6 +2377,8 @@ export const downloadGetMaxConcurrent = () =>
 
 export const downloadVerifyFile = (path: string, expectedChecksum: string) =>
   invoke<VerifyResult>("download_verify_file", { path, expectedChecksum });
+export const downloadValidatePartials = (repair?: boolean) =>
+  invoke<PartialValidation[]>("download_validate_partials", { repair });
 
 export const downloadOpenFile = (path: string) =>
   invoke<void>("download_open_file", { path });
@@ -1758,6 +2431,34 @@ export const downloadHistoryClear = (days?: number) =>
 export const downloadHistoryRemove = (id: string) =>
   invoke<boolean>("download_history_remove", { id });
 
+export const downloadHistoryExport = (
+  format: DownloadHistoryExportFormat,
+  path: string,
+  filter?: DownloadHistoryFilter,
+) =>
+  invoke<number>("download_history_export", {
+    format,
+    path,
+    filter: filter ?? null,
+  });
+
+export const downloadHistoryImport = (
+  path: string,
+  format?: DownloadHistoryExportFormat,
+) =>
+  invoke<DownloadHistoryImportResult>("download_history_import", {
+    path,
+    format: format ?? null,
+  });
+
+// Bandwidth usage commands
+export const downloadUsageStats = (period?: DownloadUsagePeriod) =>
+  invoke<DownloadUsageStats>("download_usage_stats", {
+    period: period ?? null,
+  });
+
+export const downloadUsageReset = () => invoke<number>("download_usage_reset");
+
 // Disk space commands
 export const diskSpaceGet = (path: string) =>
   invoke<DiskSpaceInfo>("disk_space_get", { path });
@@ -1786,17 +2487,30 @@ export async function listenDownloadTaskStarted(
   });
 }
 
-export async function listenDownloadTaskProgress(
-  callback: (taskId: string, progress: DownloadProgress) => void,
+// Progress is coalesced per tick into one event covering every active task
+export async function listenDownloadProgressBatch(
+  callback: (
+    updates: { task_id: string; progress: DownloadProgress }[],
+  ) => void,
 ): Promise<UnlistenFn> {
-  return listen<{ task_id: string; progress: DownloadProgress }>(
-    "download-task-progress",
+  return listen<{ updates: { task_id: string; progress: DownloadProgress }[] }>(
+    "download-progress-batch",
     (event) => {
-      callback(event.payload.task_id, event.payload.progress);
+      callback(event.payload.updates);
     },
   );
 }
 
+export async function listenDownloadTaskProgress(
+  callback: (taskId: string, progress: DownloadProgress) => void,
+): Promise<UnlistenFn> {
+  return listenDownloadProgressBatch((updates) => {
+    for (const update of updates) {
+      callback(update.task_id, update.progress);
+    }
+  });
+}
+
 export async function listenDownloadTaskCompleted(
   callback: (taskId: string) => void,
 ): Promise<UnlistenFn> {
@@ -1855,6 +2569,21 @@ export async function listenDownloadTaskCancelled(
   });
 }
 
+export async function listenDownloadTaskStalled(
+  callback: (taskId: string, idleSecs: number, retrying: boolean) => void,
+): Promise<UnlistenFn> {
+  return listen<{ task_id: string; idle_secs: number; retrying: boolean }>(
+    "download-task-stalled",
+    (event) => {
+      callback(
+        event.payload.task_id,
+        event.payload.idle_secs,
+        event.payload.retrying,
+      );
+    },
+  );
+}
+
 export async function listenDownloadTaskExtracting(
   callback: (taskId: string) => void,
 ): Promise<UnlistenFn> {
@@ -1863,6 +2592,28 @@ export async function listenDownloadTaskExtracting(
   });
 }
 
+export async function listenDownloadTaskExtractProgress(
+  callback: (taskId: string, progress: ExtractProgress) => void,
+): Promise<UnlistenFn> {
+  return listen<{ task_id: string; progress: ExtractProgress }>(
+    "download-task-extract-progress",
+    (event) => {
+      callback(event.payload.task_id, event.payload.progress);
+    },
+  );
+}
+
+export async function listenDownloadExtractProgress(
+  callback: (archivePath: string, progress: ExtractProgress) => void,
+): Promise<UnlistenFn> {
+  return listen<{ archive_path: string; progress: ExtractProgress }>(
+    "download-extract-progress",
+    (event) => {
+      callback(event.payload.archive_path, event.payload.progress);
+    },
+  );
+}
+
 export async function listenDownloadTaskExtracted(
   callback: (taskId: string, files: string[]) => void,
 ): Promise<UnlistenFn> {
@@ -1885,6 +2636,14 @@ export async function listenDownloadQueueUpdated(
   );
 }
 
+export async function listenDownloadQueueRestored(
+  callback: (summary: DownloadQueueRestored) => void,
+): Promise<UnlistenFn> {
+  return listen<DownloadQueueRestored>("download-queue-restored", (event) => {
+    callback(event.payload);
+  });
+}
+
 // ===== System Tray Commands =====
 
 export const traySetIconState = (iconState: TrayIconState) =>
@@ -1968,6 +2727,52 @@ export const traySendNotification = (
     event: options.event ?? null,
   });
 
+// Notification center commands
+export const notificationsList = (filter?: NotificationFilter) =>
+  invoke<NotificationRecord[]>("notifications_list", {
+    filter: filter ?? null,
+  });
+
+export const notificationsMarkRead = (ids?: string[]) =>
+  invoke<number>("notifications_mark_read", { ids: ids ?? null });
+
+export const notificationsClear = (ids?: string[]) =>
+  invoke<number>("notifications_clear", { ids: ids ?? null });
+
+export const notificationsGetUnreadCount = () =>
+  invoke<number>("notifications_get_unread_count");
+
+export async function listenNotificationAdded(
+  callback: (notification: NotificationRecord) => void,
+): Promise<UnlistenFn> {
+  return listen<NotificationRecord>("notification-added", (event) => {
+    callback(event.payload);
+  });
+}
+
+export async function listenNotificationsUnreadChanged(
+  callback: (count: number) => void,
+): Promise<UnlistenFn> {
+  return listen<number>("notifications-unread-changed", (event) => {
+    callback(event.payload);
+  });
+}
+
+// Deep link commands
+export const deeplinkParse = (url: string) =>
+  invoke<DeepLinkRequest>("deeplink_parse", { url });
+
+export const deeplinkTakePending = () =>
+  invoke<DeepLinkRequest[]>("deeplink_take_pending");
+
+export async function listenDeepLinkReceived(
+  callback: (request: DeepLinkRequest) => void,
+): Promise<UnlistenFn> {
+  return listen<DeepLinkRequest>("deep-link-received", (event) => {
+    callback(event.payload);
+  });
+}
+
 export const trayRebuild = () => invoke<void>("tray_rebuild");
 
 export const traySetMinimizeToTray = (enabled: boolean) =>
@@ -2125,12 +2930,16 @@ export const customRuleDetect = (envType: string, startPath: string) =>
 
 /** Detect all versions using custom rules */
 export const customRuleDetectAll = (startPath: string) =>
-  invoke<CustomDetectionResult[]>("custom_rule_detect_all", { startPath });
+  invoke<CustomDetectionReport>("custom_rule_detect_all", { startPath });
 
 /** Test a rule against a specific file */
 export const customRuleTest = (rule: CustomDetectionRule, testPath: string) =>
   invoke<TestRuleResult>("custom_rule_test", { rule, testPath });
 
+/** Approve the command a rule runs, so command lines off the allowlist may execute */
+export const customRuleApproveCommand = (ruleId: string) =>
+  invoke<void>("custom_rule_approve_command", { ruleId });
+
 /** Validate a regex pattern */
 export const customRuleValidateRegex = (pattern: string) =>
   invoke<RegexValidation>("custom_rule_validate_regex", { pattern });
@@ -2150,6 +2959,36 @@ export const customRuleListByEnv = (envType: string) =>
 export const customRuleExtractionTypes = () =>
   invoke<ExtractionTypeInfo[]>("custom_rule_extraction_types");
 
+// ===== Custom Environment Type Commands =====
+
+/** List all user-defined environment types */
+export const customEnvTypeList = () =>
+  invoke<CustomEnvTypeDefinition[]>("custom_env_type_list");
+
+/** Get a user-defined environment type by ID */
+export const customEnvTypeGet = (id: string) =>
+  invoke<CustomEnvTypeDefinition | null>("custom_env_type_get", { id });
+
+/** Problems that would prevent saving a definition (empty when valid) */
+export const customEnvTypeValidate = (definition: CustomEnvTypeDefinition) =>
+  invoke<string[]>("custom_env_type_validate", { definition });
+
+/** Add a user-defined environment type */
+export const customEnvTypeAdd = (definition: CustomEnvTypeDefinition) =>
+  invoke<void>("custom_env_type_add", { definition });
+
+/** Update a user-defined environment type */
+export const customEnvTypeUpdate = (definition: CustomEnvTypeDefinition) =>
+  invoke<void>("custom_env_type_update", { definition });
+
+/** Delete a user-defined environment type */
+export const customEnvTypeDelete = (id: string) =>
+  invoke<void>("custom_env_type_delete", { id });
+
+/** Approve the commands a user-defined environment type runs */
+export const customEnvTypeApproveCommands = (id: string) =>
+  invoke<void>("custom_env_type_approve_commands", { id });
+
 // ===== Health Check Commands =====
 
 /** Check health of all environments and package managers */
@@ -2177,6 +3016,43 @@ export const healthCheckFix = (remediationId: string, dryRun = true) =>
     dryRun,
   });
 
+// ===== Job Commands =====
+
+/** List running jobs followed by job history, newest first */
+export const jobsList = (filter?: {
+  kind?: JobKind;
+  status?: JobStatus;
+  limit?: number;
+}) =>
+  invoke<JobRecord[]>("jobs_list", {
+    kind: filter?.kind ?? null,
+    status: filter?.status ?? null,
+    limit: filter?.limit ?? null,
+  });
+
+export const jobsGet = (id: string) =>
+  invoke<JobRecord | null>("jobs_get", { id });
+
+/** Request cancellation of a running job */
+export const jobsCancel = (id: string) => invoke<boolean>("jobs_cancel", { id });
+
+export async function listenJobUpdated(
+  callback: (event: JobEvent) => void,
+): Promise<UnlistenFn> {
+  return listen<JobEvent>("job-updated", (event) => {
+    callback(event.payload);
+  });
+}
+
+/** Jobs found running from the previous session, emitted once at startup */
+export async function listenJobsInterrupted(
+  callback: (jobs: JobRecord[]) => void,
+): Promise<UnlistenFn> {
+  return listen<JobRecord[]>("jobs-interrupted", (event) => {
+    callback(event.payload);
+  });
+}
+
 // ===== Environment Profiles Commands =====
 
 /** List all profiles */
@@ -2218,13 +3094,28 @@ export const profileDelete = (id: string) =>
 export const profileApply = (id: string) =>
   invoke<ProfileApplyResult>("profile_apply", { id });
 
-/** Export a profile to JSON */
-export const profileExport = (id: string) =>
-  invoke<string>("profile_export", { id });
+/**
+ * Export a profile to JSON. With `redactSecrets`, env snapshot values whose
+ * keys match `secretPatterns` (default: the `envvar.secret_key_patterns`
+ * setting) are replaced with placeholders.
+ */
+export const profileExport = (
+  id: string,
+  options?: { redactSecrets?: boolean; secretPatterns?: string[] },
+) =>
+  invoke<string>("profile_export", {
+    id,
+    redactSecrets: options?.redactSecrets ?? null,
+    secretPatterns: options?.secretPatterns ?? null,
+  });
+
+/** Validate a profile payload and report what importing it would require */
+export const profileImportPreview = (json: string) =>
+  invoke<ProfileImportPreview>("profile_import_preview", { json });
 
-/** Import a profile from JSON */
-export const profileImport = (json: string) =>
-  invoke<EnvironmentProfile>("profile_import", { json });
+/** Import a profile from JSON, supplying values for redacted secrets */
+export const profileImport = (json: string, secrets?: Record<string, string>) =>
+  invoke<EnvironmentProfile>("profile_import", { json, secrets: secrets ?? null });
 
 /** Create a profile from current environment state */
 export const profileCreateFromCurrent = (
@@ -2248,6 +3139,35 @@ export const profileCaptureWslSnapshot = () =>
 export const profileApplyWslSnapshot = (snapshot: WslProfileSnapshot) =>
   invoke<WslProfileApplyResult>("profile_apply_wsl_snapshot", { snapshot });
 
+// ===== Workspace Commands =====
+
+/** List all workspaces */
+export const workspaceList = () => invoke<Workspace[]>("workspace_list");
+
+/** Create a workspace grouping the git repos under `rootPaths` */
+export const workspaceCreate = (
+  name: string,
+  rootPaths: string[],
+  excludeGlobs?: string[],
+) =>
+  invoke<Workspace>("workspace_create", { name, rootPaths, excludeGlobs });
+
+/** Delete a workspace */
+export const workspaceDelete = (id: string) =>
+  invoke<void>("workspace_delete", { id });
+
+/** Discover repos and detect their pins; unchanged repos are skipped unless `full` */
+export const workspaceScan = (id: string, full?: boolean) =>
+  invoke<WorkspaceScanResult>("workspace_scan", { id, full });
+
+/** Per-repo pins, missing toolchains and ahead/behind counts */
+export const workspaceStatus = (id: string) =>
+  invoke<WorkspaceStatus>("workspace_status", { id });
+
+/** Install every missing toolchain across the workspace as one job */
+export const workspaceApplyEnvs = (id: string) =>
+  invoke<WorkspaceApplyResult>("workspace_apply_envs", { id });
+
 // ============================================================================
 // GitHub Commands
 // ============================================================================
@@ -2257,9 +3177,14 @@ import type {
   GitHubTagInfo,
   GitHubReleaseInfo,
   GitHubAssetInfo,
+  AssetExplanation,
+  AssetMatchRule,
+  AssetPickExplanation,
+  AssetVerdict,
   GitHubWorkflowArtifactInfo,
   GitHubParsedRepo,
   GitHubRepoInfoResponse,
+  ApiRateLimitStatus,
 } from "@/types/github";
 
 export type {
@@ -2267,9 +3192,14 @@ export type {
   GitHubTagInfo,
   GitHubReleaseInfo,
   GitHubAssetInfo,
+  AssetExplanation,
+  AssetMatchRule,
+  AssetPickExplanation,
+  AssetVerdict,
   GitHubWorkflowArtifactInfo,
   GitHubParsedRepo,
   GitHubRepoInfoResponse,
+  ApiRateLimitStatus,
 };
 
 /** Parse a GitHub URL or owner/repo string */
@@ -2317,6 +3247,18 @@ export const githubGetReleaseAssets = (
     token: token || null,
   });
 
+/** Score every asset of a release for this system and show which rule picked it */
+export const githubPickAssetExplain = (
+  repo: string,
+  tag: string,
+  token?: string,
+) =>
+  invoke<AssetPickExplanation>("github_pick_asset_explain", {
+    repo,
+    tag,
+    token: token || null,
+  });
+
 /** List recent workflow artifacts for a repository */
 export const githubListWorkflowArtifacts = (repo: string, token?: string) =>
   invoke<GitHubWorkflowArtifactInfo[]>("github_list_workflow_artifacts", {
@@ -2332,6 +3274,7 @@ export const githubDownloadAsset = (
   assetName: string,
   destination: string,
   token?: string,
+  tag?: string,
 ) =>
   invoke<string>("github_download_asset", {
     repo,
@@ -2340,6 +3283,7 @@ export const githubDownloadAsset = (
     assetName,
     destination,
     token: token || null,
+    tag: tag || null,
   });
 
 /** Download source archive (zip/tar.gz) to the download queue */
@@ -2390,6 +3334,13 @@ export const githubClearToken = () =>
 export const githubValidateToken = (token: string) =>
   invoke<boolean>("github_validate_token", { token });
 
+/** Get the GitHub API quota; `refresh` asks GitHub instead of using the last seen headers */
+export const githubRateLimitStatus = (refresh?: boolean, token?: string) =>
+  invoke<ApiRateLimitStatus>("github_rate_limit_status", {
+    refresh: refresh ?? null,
+    token: token || null,
+  });
+
 // ============================================================================
 // GitLab Commands
 // ============================================================================
@@ -2508,6 +3459,7 @@ export const gitlabDownloadAsset = (
   destination: string,
   token?: string,
   instanceUrl?: string,
+  tag?: string,
 ) =>
   invoke<string>("gitlab_download_asset", {
     project,
@@ -2516,6 +3468,7 @@ export const gitlabDownloadAsset = (
     destination,
     token: token || null,
     instanceUrl: instanceUrl || null,
+    tag: tag || null,
   });
 
 /** Download source archive from GitLab to the download queue */
@@ -2671,6 +3624,13 @@ export const gitlabSetInstanceUrl = (url: string) =>
 export const gitlabGetInstanceUrl = () =>
   invoke<string | null>("gitlab_get_instance_url");
 
+/** Get the GitLab API quota for the instance and token from the last response that reported one */
+export const gitlabRateLimitStatus = (token?: string, instanceUrl?: string) =>
+  invoke<ApiRateLimitStatus | null>("gitlab_rate_limit_status", {
+    token: token || null,
+    instanceUrl: instanceUrl || null,
+  });
+
 // ============================================================================
 // Filesystem Utility Commands
 // ============================================================================
@@ -2785,6 +3745,30 @@ export const wslSetConfig = (section: string, key: string, value?: string) =>
 export const wslSetNetworkingMode = (mode: string) =>
   invoke<void>("wsl_set_networking_mode", { mode });
 
+/** Known sections and keys of .wslconfig or /etc/wsl.conf */
+export const wslConfigSchema = (kind: WslConfigKind) =>
+  invoke<WslConfigKeyDoc[]>("wsl_config_schema", { kind });
+
+/** Check WSL config content key by key without writing it */
+export const wslConfigValidate = (content: string, kind: WslConfigKind) =>
+  invoke<WslConfigDiagnostic[]>("wsl_config_validate", { content, kind });
+
+/** Set or remove one key in .wslconfig or a distro's /etc/wsl.conf, keeping comments */
+export const wslConfigSetKey = (
+  kind: WslConfigKind,
+  section: string,
+  key: string,
+  value?: string,
+  distro?: string,
+) =>
+  invoke<WslConfigSetKeyResult>("wsl_config_set_key", {
+    kind,
+    distro,
+    section,
+    key,
+    value,
+  });
+
 /** Get disk usage for a WSL distribution */
 export const wslDiskUsage = (name: string) =>
   invoke<WslDiskUsage>("wsl_disk_usage", { name });
@@ -2886,13 +3870,52 @@ export const wslCloneDistro = (
 export const wslTotalDiskUsage = () =>
   invoke<[number, [string, number][]]>("wsl_total_disk_usage");
 
-/** Launch multiple WSL distributions in parallel */
-export const wslBatchLaunch = (names: string[]) =>
-  invoke<[string, boolean, string][]>("wsl_batch_launch", { names });
+/** Launch multiple WSL distributions in parallel */
+export const wslBatchLaunch = (names: string[]) =>
+  invoke<[string, boolean, string][]>("wsl_batch_launch", { names });
+
+/** Terminate multiple WSL distributions in parallel */
+export const wslBatchTerminate = (names: string[]) =>
+  invoke<[string, boolean, string][]>("wsl_batch_terminate", { names });
+
+/** Run a command in several distributions; stopped ones are skipped unless `autoStart` */
+export const wslBatchExec = (
+  distros: string[],
+  command: string,
+  parallel: boolean,
+  options?: { user?: string; timeoutSecs?: number; autoStart?: boolean },
+) =>
+  invoke<WslBatchItemResult[]>("wsl_batch_exec", {
+    distros,
+    command,
+    parallel,
+    user: options?.user ?? null,
+    timeoutSecs: options?.timeoutSecs ?? null,
+    autoStart: options?.autoStart ?? null,
+  });
+
+/** Refresh and upgrade packages in several distributions with their own package manager */
+export const wslBatchUpdate = (
+  distros: string[],
+  options?: { timeoutSecs?: number; autoStart?: boolean },
+) =>
+  invoke<WslBatchItemResult[]>("wsl_batch_update", {
+    distros,
+    timeoutSecs: options?.timeoutSecs ?? null,
+    autoStart: options?.autoStart ?? null,
+  });
+
+/** Back up several distributions to timestamped tar files in `destDir` */
+export const wslBatchExport = (distros: string[], destDir: string) =>
+  invoke<WslBatchItemResult[]>("wsl_batch_export", { distros, destDir });
 
-/** Terminate multiple WSL distributions in parallel */
-export const wslBatchTerminate = (names: string[]) =>
-  invoke<[string, boolean, string][]>("wsl_batch_terminate", { names });
+export async function listenWslBatchExportProgress(
+  callback: (progress: WslBatchProgress) => void,
+): Promise<UnlistenFn> {
+  return listen<WslBatchProgress>("wsl-batch-export-progress", (event) => {
+    callback(event.payload);
+  });
+}
 
 /** List all Windows port forwarding rules (netsh portproxy) */
 export const wslListPortForwards = () =>
@@ -2969,6 +3992,51 @@ export const launchWithEnv = (request: LaunchRequest) =>
 export const launchWithStreaming = (request: LaunchRequest) =>
   invoke<LaunchResult>("launch_with_streaming", { request });
 
+/** List processes started via the launch commands that are still running */
+export const launchListRunning = () =>
+  invoke<TrackedLaunchInfo[]>("launch_list_running");
+
+/** CPU, memory and child process count of a running launch */
+export const launchGetStats = (launchId: string) =>
+  invoke<LaunchStats>("launch_get_stats", { launchId });
+
+/** Stop a running launch; resolves to whether it exited without being killed */
+export const launchStop = (launchId: string, force?: boolean) =>
+  invoke<boolean>("launch_stop", { launchId, force });
+
+export async function listenLaunchStarted(
+  callback: (launch: TrackedLaunchInfo) => void,
+): Promise<UnlistenFn> {
+  return listen<TrackedLaunchInfo>("launch-started", (event) => {
+    callback(event.payload);
+  });
+}
+
+/** Resource samples, sent at the `terminal.launch_sample_interval_secs` interval */
+export async function listenLaunchResourceSample(
+  callback: (stats: LaunchStats) => void,
+): Promise<UnlistenFn> {
+  return listen<LaunchStats>("launch-resource-sample", (event) => {
+    callback(event.payload);
+  });
+}
+
+export async function listenLaunchLimitExceeded(
+  callback: (limit: LaunchLimitEvent) => void,
+): Promise<UnlistenFn> {
+  return listen<LaunchLimitEvent>("launch-limit-exceeded", (event) => {
+    callback(event.payload);
+  });
+}
+
+export async function listenLaunchExited(
+  callback: (launchId: string) => void,
+): Promise<UnlistenFn> {
+  return listen<string>("launch-exited", (event) => {
+    callback(event.payload);
+  });
+}
+
 /** Get shell activation script for a specific environment */
 export const envActivate = (
   envType: string,
@@ -3010,6 +4078,18 @@ export const whichProgram = (
 ) =>
   invoke<string | null>("which_program", { program, envType, envVersion, cwd });
 
+/** Every match for a program on PATH, in PATH order, and which one runs */
+export const whichProgramDetailed = (program: string) =>
+  invoke<ProgramMatch[]>("which_program_detailed", { program });
+
+/** List a project's runnable tasks from package.json, Makefile, justfile and Taskfile */
+export const projectTasksList = (path: string) =>
+  invoke<ProjectTask[]>("project_tasks_list", { path });
+
+/** Run a project task, streaming output as `command-output` events */
+export const projectTaskRun = (path: string, taskId: string) =>
+  invoke<LaunchResult>("project_task_run", { path, taskId });
+
 // ============================================================================
 // Shim Commands
 // ============================================================================
@@ -3020,8 +4100,15 @@ export const shimCreate = (
   envType: string,
   version: string | null,
   targetPath: string,
+  versionSwitch?: boolean,
 ) =>
-  invoke<string>("shim_create", { binaryName, envType, version, targetPath });
+  invoke<string>("shim_create", {
+    binaryName,
+    envType,
+    version,
+    targetPath,
+    versionSwitch,
+  });
 
 /** Remove a shim */
 export const shimRemove = (binaryName: string) =>
@@ -3037,6 +4124,64 @@ export const shimUpdate = (binaryName: string, version?: string) =>
 /** Regenerate all shims */
 export const shimRegenerateAll = () => invoke<void>("shim_regenerate_all");
 
+/** Generate completions for all shims, offering installed versions as `+<version>` */
+export const shimGenerateCompletions = (shellType: InitShellType) =>
+  invoke<string>("shim_generate_completions", { shellType });
+
+// ============================================================================
+// Trash Commands
+// ============================================================================
+
+/** Deleted terminal profiles, custom rules, shims and profiles, newest first */
+export const trashList = () =>
+  invoke<import("@/types/tauri").TrashEntry[]>("trash_list");
+
+/** Restore a trashed object, under a suffixed ID if the original is taken (refused for shims) */
+export const trashRestore = (id: string) =>
+  invoke<import("@/types/tauri").TrashRestoreResult>("trash_restore", { id });
+
+/** Permanently delete trash entries older than the given days, or all of them */
+export const trashPurge = (olderThanDays?: number) =>
+  invoke<number>("trash_purge", { olderThanDays: olderThanDays ?? null });
+
+// ============================================================================
+// Stack Commands
+// ============================================================================
+
+/** Capture current env versions, global packages, a terminal profile and env vars as a stack */
+export const stackCreateFromCurrent = (
+  name: string,
+  options?: {
+    description?: string;
+    packageProviders?: string[];
+    terminalProfileId?: string;
+    envVarKeys?: string[];
+  },
+) =>
+  invoke<import("@/types/tauri").Stack>("stack_create_from_current", {
+    name,
+    description: options?.description ?? null,
+    packageProviders: options?.packageProviders ?? null,
+    terminalProfileId: options?.terminalProfileId ?? null,
+    envVarKeys: options?.envVarKeys ?? null,
+  });
+
+/** Write a stack to a shareable JSON file */
+export const stackExport = (stack: import("@/types/tauri").Stack, path: string) =>
+  invoke<void>("stack_export", { stack, path });
+
+/** Read and validate a stack file */
+export const stackImport = (path: string) =>
+  invoke<import("@/types/tauri").Stack>("stack_import", { path });
+
+/** Diff a stack against this machine */
+export const stackPreview = (stack: import("@/types/tauri").Stack) =>
+  invoke<import("@/types/tauri").StackPlan>("stack_preview", { stack });
+
+/** Apply a stack; progress is emitted as `stack-apply-progress` */
+export const stackApply = (stack: import("@/types/tauri").Stack) =>
+  invoke<import("@/types/tauri").StackApplyResult>("stack_apply", { stack });
+
 // ============================================================================
 // PATH Management Commands
 // ============================================================================
@@ -3056,6 +4201,10 @@ export const pathCheck = () => invoke<boolean>("path_check");
 /** Get the command to manually add shim directory to PATH */
 export const pathGetAddCommand = () => invoke<string>("path_get_add_command");
 
+/** Get the init script that puts the shim directory on PATH for a shell */
+export const pathGetInitScript = (shellType: InitShellType) =>
+  invoke<string>("path_get_init_script", { shellType });
+
 // ============================================================================
 // Git Commands
 // ============================================================================
@@ -3789,6 +4938,28 @@ export const gitStartInteractiveRebase = (
   todo: GitRebaseTodoItem[],
 ) => invoke<string>("git_start_interactive_rebase", { path, base, todo });
 
+/** Build an interactive rebase plan (commits between base and HEAD) */
+export const gitRebasePlan = (path: string, base: string) =>
+  invoke<GitRebasePlan>("git_rebase_plan", { path, base });
+
+/** Execute a rebase plan; stops with the conflicted files on conflicts */
+export const gitRebaseExecute = (
+  path: string,
+  plan: GitRebasePlan,
+  options?: GitRebaseExecuteOptions,
+) => invoke<GitRebaseStatus>("git_rebase_execute", { path, plan, options });
+
+/** Get rebase state and conflicted files */
+export const gitRebaseStatus = (path: string) =>
+  invoke<GitRebaseStatus>("git_rebase_status", { path });
+
+/** Amend HEAD with a new message, or keep it with noEdit */
+export const gitCommitAmend = (
+  path: string,
+  message?: string,
+  noEdit?: boolean,
+) => invoke<string>("git_commit_amend", { path, message, noEdit });
+
 /** Start bisect session */
 export const gitBisectStart = (path: string, badRef: string, goodRef: string) =>
   invoke<string>("git_bisect_start", { path, badRef, goodRef });
@@ -3965,10 +5136,20 @@ export const envvarSetPersistent = (
 export const envvarRemovePersistent = (key: string, scope: EnvVarScope) =>
   invoke<EnvVarMutationResult>("envvar_remove_persistent", { key, scope });
 
+/** Apply several persistent variable / PATH edits as one transaction */
+export const envvarApplyBatch = (ops: EnvVarOperation[], scope: EnvVarScope) =>
+  invoke<EnvVarTransactionResult>("envvar_apply_batch", { ops, scope });
+
 /** Get PATH entries with existence info */
 export const envvarGetPath = (scope: EnvVarScope) =>
   invoke<PathEntryInfo[]>("envvar_get_path", { scope });
 
+/** Which copy of each managed binary wins on PATH, for the app and a login shell */
+export const pathShadowReport = (binaries?: string[]) =>
+  invoke<import("@/types/tauri").PathShadowReport>("path_shadow_report", {
+    binaries: binaries ?? null,
+  });
+
 /** Add a PATH entry */
 export const envvarAddPathEntry = (
   path: string,
@@ -4159,6 +5340,21 @@ export const terminalLaunchProfileDetailed = (
     cwd: options?.cwd,
   });
 
+/** List recorded profile launches, newest first */
+export const terminalLaunchHistory = (limit?: number, profileId?: string) =>
+  invoke<TerminalLaunchRecord[]>("terminal_launch_history", {
+    limit,
+    profileId,
+  });
+
+/** Launch a profile again with the settings of a history entry */
+export const terminalRelaunch = (historyId: string) =>
+  invoke<LaunchResult>("terminal_relaunch", { historyId });
+
+/** Pin or unpin a launch history entry */
+export const terminalLaunchHistoryPin = (historyId: string, pinned: boolean) =>
+  invoke<boolean>("terminal_launch_history_pin", { historyId, pinned });
+
 /** Read a shell config file */
 export const terminalReadConfig = (path: string) =>
   invoke<string>("terminal_read_config", { path });
@@ -4184,6 +5380,36 @@ export const terminalAppendToConfigVerified = (path: string, content: string) =>
     content,
   });
 
+/** Add or replace an alias/export in place (backed up before writing) */
+export const terminalConfigUpsertEntry = (
+  path: string,
+  shellType: ShellType,
+  kind: ShellConfigEntryKind,
+  name: string,
+  value: string,
+) =>
+  invoke<TerminalConfigMutationResult>("terminal_config_upsert_entry", {
+    path,
+    shellType,
+    kind,
+    name,
+    value,
+  });
+
+/** Remove every definition of an alias/export */
+export const terminalConfigRemoveEntry = (
+  path: string,
+  shellType: ShellType,
+  kind: ShellConfigEntryKind,
+  name: string,
+) =>
+  invoke<TerminalConfigMutationResult>("terminal_config_remove_entry", {
+    path,
+    shellType,
+    kind,
+    name,
+  });
+
 /** Get parsed config entries (aliases, exports, sources) */
 export const terminalGetConfigEntries = (path: string, shellType: ShellType) =>
   invoke<ShellConfigEntries>("terminal_get_config_entries", {
@@ -4211,6 +5437,15 @@ export const terminalValidateConfigContent = (
     shellType,
   });
 
+export const shellProfileAnalyze = (shellId: string) =>
+  invoke<ShellProfileAnalysis>("shell_profile_analyze", { shellId });
+
+export const shellProfileApplyFix = (shellId: string, findingId: string) =>
+  invoke<ShellProfileFixResult>("shell_profile_apply_fix", {
+    shellId,
+    findingId,
+  });
+
 /** Get editor metadata for a shell config target */
 export const terminalGetConfigEditorMetadata = (
   path: string,
@@ -4327,18 +5562,86 @@ export const terminalWriteConfigVerified = (path: string, content: string) =>
 export const terminalPsInstallModule = (name: string, scope: string) =>
   invoke<void>("terminal_ps_install_module", { name, scope });
 
-/** Uninstall a PowerShell module */
-export const terminalPsUninstallModule = (name: string) =>
-  invoke<void>("terminal_ps_uninstall_module", { name });
+/** Uninstall a PowerShell module (refuses when other modules require it unless forced) */
+export const terminalPsUninstallModule = (name: string, force?: boolean) =>
+  invoke<PSModuleUninstallResult>("terminal_ps_uninstall_module", {
+    name,
+    force: force ?? null,
+  });
 
 /** Update a PowerShell module */
 export const terminalPsUpdateModule = (name: string) =>
   invoke<void>("terminal_ps_update_module", { name });
 
+/** Update all PSGallery modules, emitting per-module progress events */
+export const terminalPsUpdateAllModules = () =>
+  invoke<PSModuleUpdateResult[]>("terminal_ps_update_all_modules");
+
+export async function listenPsModuleUpdateProgress(
+  callback: (progress: PSModuleUpdateProgress) => void,
+): Promise<UnlistenFn> {
+  return listen<PSModuleUpdateProgress>(
+    "terminal-ps-module-update-progress",
+    (event) => {
+      callback(event.payload);
+    },
+  );
+}
+
 /** Search PSGallery for modules */
 export const terminalPsFindModule = (query: string) =>
   invoke<PSModuleInfo[]>("terminal_ps_find_module", { query });
 
+/** Open an interactive PTY session for a terminal profile */
+export const terminalSessionOpen = (
+  profileId: string,
+  cols: number,
+  rows: number,
+) =>
+  invoke<TerminalSessionInfo>("terminal_session_open", {
+    profileId,
+    cols,
+    rows,
+  });
+
+/** Send raw input (keystrokes, pasted text) to a session */
+export const terminalSessionWrite = (sessionId: string, data: string) =>
+  invoke<void>("terminal_session_write", { sessionId, data });
+
+/** Resize a session's PTY */
+export const terminalSessionResize = (
+  sessionId: string,
+  cols: number,
+  rows: number,
+) => invoke<void>("terminal_session_resize", { sessionId, cols, rows });
+
+/** Kill a session's process and release its PTY */
+export const terminalSessionClose = (sessionId: string) =>
+  invoke<void>("terminal_session_close", { sessionId });
+
+/** List open terminal sessions */
+export const terminalSessionList = () =>
+  invoke<TerminalSessionInfo[]>("terminal_session_list");
+
+export async function listenTerminalSessionOutput(
+  callback: (event: TerminalSessionOutputEvent) => void,
+): Promise<UnlistenFn> {
+  return listen<TerminalSessionOutputEvent>(
+    "terminal-session-output",
+    (event) => {
+      callback(event.payload);
+    },
+  );
+}
+
+export async function listenTerminalSessionExit(
+  callback: (event: TerminalSessionExitEvent) => void,
+): Promise<UnlistenFn> {
+  return listen<TerminalSessionExitEvent>("terminal-session-exit", (event) => {
+    callback(event.payload);
+  });
+}
+
 /** List all terminal profile templates (built-in + custom) */
 export const terminalListTemplates = () =>
   invoke<TerminalProfileTemplate[]>("terminal_list_templates");
@@ -4493,6 +5796,32 @@ export const pluginDisable = (pluginId: string) =>
 export const pluginReload = (pluginId: string) =>
   invoke<void>("plugin_reload", { pluginId });
 
+/** Hot-reload a locally imported plugin whenever its built WASM or manifest changes */
+export const pluginDevWatch = (pluginId: string, wasmPath: string) =>
+  invoke<import("@/types/plugin").PluginDevWatchInfo>("plugin_dev_watch", {
+    pluginId,
+    wasmPath,
+  });
+
+/** Stop hot-reloading a plugin; resolves to whether a watch was active */
+export const pluginDevUnwatch = (pluginId: string) =>
+  invoke<boolean>("plugin_dev_unwatch", { pluginId });
+
+/** List active plugin dev watches */
+export const pluginDevWatchList = () =>
+  invoke<import("@/types/plugin").PluginDevWatchInfo[]>("plugin_dev_watch_list");
+
+export async function listenPluginReloaded(
+  callback: (event: import("@/types/plugin").PluginReloadedEvent) => void,
+): Promise<UnlistenFn> {
+  return listen<import("@/types/plugin").PluginReloadedEvent>(
+    "plugin-reloaded",
+    (event) => {
+      callback(event.payload);
+    },
+  );
+}
+
 /** Call a tool function on a plugin */
 export const pluginCallTool = (
   pluginId: string,
@@ -4562,9 +5891,22 @@ export const pluginGrantPermission = (pluginId: string, permission: string) =>
 export const pluginRevokePermission = (pluginId: string, permission: string) =>
   invoke<void>("plugin_revoke_permission", { pluginId, permission });
 
-/** Get plugin data directory path */
+/** Get plugin data directory path with storage usage vs quota */
 export const pluginGetDataDir = (pluginId: string) =>
-  invoke<string>("plugin_get_data_dir", { pluginId });
+  invoke<import("@/types/plugin").PluginDataDirInfo>("plugin_get_data_dir", {
+    pluginId,
+  });
+
+/** List what a plugin has written to its key-value store */
+export const pluginStorageInspect = (pluginId: string) =>
+  invoke<import("@/types/plugin").PluginStorageInspection>(
+    "plugin_storage_inspect",
+    { pluginId },
+  );
+
+/** Delete keys from a plugin's key-value store; clears everything when keys is omitted */
+export const pluginStorageClear = (pluginId: string, keys?: string[]) =>
+  invoke<number>("plugin_storage_clear", { pluginId, keys: keys ?? null });
 
 /** Get plugin locale data for i18n */
 export const pluginGetLocales = (pluginId: string) =>
@@ -4681,6 +6023,35 @@ export const pluginCheckAllUpdates = () =>
     "plugin_check_all_updates",
   );
 
+/** Fetch and verify the remote marketplace index */
+export const pluginMarketplaceRefresh = () =>
+  invoke<import("@/types/plugin").MarketplaceIndexSummary>(
+    "plugin_marketplace_refresh",
+  );
+
+/** List plugins from the marketplace index */
+export const pluginMarketplaceList = (
+  filter?: import("@/types/plugin").MarketplaceFilter,
+) =>
+  invoke<import("@/types/plugin").MarketplacePluginListing[]>(
+    "plugin_marketplace_list",
+    { filter: filter ?? null },
+  );
+
+/** Install or upgrade a plugin from the marketplace index */
+export const pluginMarketplaceInstall = (
+  id: string,
+  grantPermissions?: string[],
+) =>
+  invoke<import("@/types/plugin").MarketplaceInstallReport>(
+    "plugin_marketplace_install",
+    { id, grantPermissions: grantPermissions ?? null },
+  );
+
+/** Compare installed plugins against the marketplace index */
+export const pluginCheckUpdates = () =>
+  invoke<import("@/types/plugin").PluginUpdateInfo[]>("plugin_check_updates");
+
 /** Update all plugins that have available updates */
 export const pluginUpdateAll = () =>
   invoke<Array<{ Ok?: string; Err?: string }>>("plugin_update_all");
@@ -4770,11 +6141,71 @@ export const wingetInstallAdvanced = (options: {
     force: options.force ?? false,
   });
 
+/** Install an environment type (python, node, git, ...) via its mapped winget package */
+export const wingetInstallEnvironment = (envType: string, version?: string) =>
+  invoke<import("@/types/tauri").WingetEnvInstallResult>(
+    "winget_install_environment",
+    { envType, version },
+  );
+
+// ============================================================================
+// Scoop-specific commands
+// ============================================================================
+
+// Bucket management
+export const scoopBucketList = () =>
+  invoke<import("@/types/tauri").ScoopBucket[]>("scoop_bucket_list");
+export const scoopBucketAdd = (name: string, repo?: string) =>
+  invoke<void>("scoop_bucket_add", { name, repo });
+export const scoopBucketRemove = (name: string) =>
+  invoke<void>("scoop_bucket_remove", { name });
+
+// Maintenance
+export const scoopCleanup = (
+  app?: string,
+  options?: { cache?: boolean; global?: boolean },
+) =>
+  invoke<void>("scoop_cleanup", {
+    app,
+    cache: options?.cache ?? false,
+    global: options?.global ?? false,
+  });
+export const scoopHold = (name: string, global?: boolean) =>
+  invoke<void>("scoop_hold", { name, global: global ?? false });
+export const scoopUnhold = (name: string, global?: boolean) =>
+  invoke<void>("scoop_unhold", { name, global: global ?? false });
+
+// ============================================================================
+// Chocolatey-specific commands
+// ============================================================================
+
+// Source management
+export const chocoSourceList = () =>
+  invoke<import("@/types/tauri").ChocoSource[]>("choco_source_list");
+export const chocoSourceAdd = (name: string, url: string, priority?: number) =>
+  invoke<void>("choco_source_add", { name, url, priority });
+export const chocoSourceRemove = (name: string) =>
+  invoke<void>("choco_source_remove", { name });
+
+// Pin management
+export const chocoPinList = () =>
+  invoke<import("@/types/tauri").ChocoPin[]>("choco_pin_list");
+/** Pin a package (at `version`, or its installed version), or unpin it with `pinned: false` */
+export const chocoPin = (name: string, pinned: boolean, version?: string) =>
+  invoke<void>("choco_pin", { name, version, pinned });
+
 // ============================================================================
 // Xmake/Xrepo Commands
 // ============================================================================
 
-export type { XmakeRepo } from "@/types/tauri";
+export type {
+  XmakeRepo,
+  XmakeDependency,
+  XmakeProjectDeps,
+  XmakeDepsExportFormat,
+  XmakeCacheDir,
+  XmakeCacheStats,
+} from "@/types/tauri";
 
 // Repository management
 export const xmakeListRepos = () =>
@@ -4791,6 +6222,18 @@ export const xmakeCleanCache = (packages?: string[], cacheOnly?: boolean) =>
     packages,
     cacheOnly: cacheOnly ?? false,
   });
+export const xmakeCacheStats = () =>
+  invoke<import("@/types/tauri").XmakeCacheStats>("xmake_cache_stats");
+
+// Project dependencies
+export const xmakeProjectDeps = (path: string) =>
+  invoke<import("@/types/tauri").XmakeProjectDeps>("xmake_project_deps", {
+    path,
+  });
+export const xmakeProjectDepsExport = (
+  path: string,
+  format: import("@/types/tauri").XmakeDepsExportFormat,
+) => invoke<string>("xmake_project_deps_export", { path, format });
 
 // Virtual environment
 export const xmakeEnvShow = (packages: string[]) =>
@@ -4843,6 +6286,22 @@ export const brewPin = (name: string) => invoke<string>("brew_pin", { name });
 export const brewUnpin = (name: string) =>
   invoke<string>("brew_unpin", { name });
 
+// Casks
+export const brewCaskList = () =>
+  invoke<import("@/types/tauri").BrewCask[]>("brew_cask_list");
+export const brewCaskInfo = (name: string) =>
+  invoke<import("@/types/tauri").BrewCask>("brew_cask_info", { name });
+export const brewCaskOutdated = (greedy?: boolean) =>
+  invoke<import("@/types/tauri").BrewCaskOutdated[]>("brew_cask_outdated", {
+    greedy,
+  });
+export const brewCaskInstall = (name: string) =>
+  invoke<string>("brew_cask_install", { name });
+export const brewCaskUninstall = (name: string, zap?: boolean) =>
+  invoke<string>("brew_cask_uninstall", { name, zap });
+export const brewCaskUpgrade = (name?: string, greedy?: boolean) =>
+  invoke<string>("brew_cask_upgrade", { name, greedy });
+
 // Config & analytics
 export const brewGetConfig = () =>
   invoke<import("@/types/tauri").BrewConfigInfo>("brew_get_config");
@@ -4911,12 +6370,20 @@ export const uvRemove = (
   packages: string[],
   dev: boolean = false,
 ) => invoke<string>("uv_remove", { path, packages, dev });
-export const uvSync = (path: string, frozen: boolean = false) =>
-  invoke<string>("uv_sync", { path, frozen });
+export const uvSync = (
+  path: string,
+  frozen: boolean = false,
+  packageName?: string,
+) => invoke<string>("uv_sync", { path, frozen, package: packageName });
 export const uvLock = (path: string, upgrade: boolean = false) =>
   invoke<string>("uv_lock", { path, upgrade });
 export const uvRun = (path: string, command: string[]) =>
   invoke<import("@/types/tauri").UvRunResult>("uv_run", { path, command });
+export const uvRunScript = (scriptPath: string, scriptArgs: string[] = []) =>
+  invoke<import("@/types/tauri").UvRunResult>("uv_run_script", {
+    scriptPath,
+    scriptArgs,
+  });
 export const uvTree = (path: string) => invoke<string>("uv_tree", { path });
 export const uvVenvCreate = (path: string, python?: string) =>
   invoke<string>("uv_venv_create", { path, python });
@@ -4930,6 +6397,8 @@ export const uvPythonList = (onlyInstalled: boolean = false) =>
   });
 export const uvPythonPin = (path: string, version: string) =>
   invoke<string>("uv_python_pin", { path, version });
+export const uvPythonFind = (request?: string, path?: string) =>
+  invoke<string>("uv_python_find", { request, path });
 export const uvPipCompile = (path: string, input: string, output?: string) =>
   invoke<string>("uv_pip_compile", { path, input, output });
 export const uvSelfUpdate = () => invoke<string>("uv_self_update");
@@ -4946,6 +6415,112 @@ export const uvToolRun = (name: string, toolArgs: string[]) =>
     name,
     toolArgs,
   });
+export const uvWorkspaceList = (path: string) =>
+  invoke<import("@/types/tauri").UvWorkspaceMember[]>("uv_workspace_list", {
+    path,
+  });
+export const uvScriptDeps = (scriptPath: string) =>
+  invoke<import("@/types/tauri").UvScriptMetadata>("uv_script_deps", {
+    scriptPath,
+  });
+
+// ============================================================================
+// Volta Commands
+// ============================================================================
+
+export const voltaStatus = () =>
+  invoke<import("@/types/tauri").VoltaStatus>("volta_status");
+export const voltaList = () =>
+  invoke<import("@/types/tauri").VoltaToolchain[]>("volta_list");
+export const voltaInstall = (tool: string, version?: string) =>
+  invoke<string>("volta_install", { tool, version: version ?? null });
+export const voltaPin = (tool: string, projectPath: string, version?: string) =>
+  invoke<string>("volta_pin", {
+    tool,
+    version: version ?? null,
+    projectPath,
+  });
+export const voltaWhich = (tool: string) =>
+  invoke<import("@/types/tauri").VoltaWhichResult>("volta_which", { tool });
+
+// ============================================================================
+// FVM (Flutter) Commands
+// ============================================================================
+
+export const fvmListInstalled = () =>
+  invoke<import("@/types/tauri").FvmSdk[]>("fvm_list_installed");
+export const fvmListReleases = (channel?: string) =>
+  invoke<import("@/types/tauri").FvmRelease[]>("fvm_list_releases", {
+    channel: channel ?? null,
+  });
+export const fvmInstall = (version: string) =>
+  invoke<string>("fvm_install", { version });
+export const fvmUse = (projectPath: string, version: string, pin = false) =>
+  invoke<string>("fvm_use", { projectPath, version, pin });
+export const fvmRemove = (version: string) =>
+  invoke<string>("fvm_remove", { version });
+export const fvmDoctor = () =>
+  invoke<import("@/types/tauri").FvmDoctorReport>("fvm_doctor");
+export const fvmProjectStatus = (projectPath: string) =>
+  invoke<import("@/types/tauri").FvmProjectStatus>("fvm_project_status", {
+    projectPath,
+  });
+/** Download the standalone FVM installer for review; pass its `sha256` to `fvmSelfInstall` */
+export const fvmInstallScriptPreview = () =>
+  invoke<import("@/types/tauri").InstallScript>("fvm_install_script_preview");
+/** `expectedSha256` is only needed when no Dart SDK is on PATH */
+export const fvmSelfInstall = (expectedSha256?: string) =>
+  invoke<string>("fvm_self_install", { expectedSha256: expectedSha256 ?? null });
+
+// ============================================================================
+// juliaup / rig Commands
+// ============================================================================
+
+export const juliaupSelfInstall = () => invoke<string>("juliaup_self_install");
+export const rigSelfInstall = () => invoke<string>("rig_self_install");
+
+// ============================================================================
+// SDKMAN Commands
+// ============================================================================
+
+/** Download the SDKMAN installer for review; pass its `sha256` to `sdkmanSelfInstall` */
+export const sdkmanInstallScriptPreview = () =>
+  invoke<import("@/types/tauri").InstallScript>("sdkman_install_script_preview");
+export const sdkmanSelfInstall = (expectedSha256: string) =>
+  invoke<import("@/types/tauri").SdkmanSelfInstall>("sdkman_self_install", {
+    expectedSha256,
+  });
+
+// ============================================================================
+// Mirror Sync Commands
+// ============================================================================
+
+export const mirrorSyncTools = (
+  preview: boolean,
+  force?: boolean,
+  tools?: import("@/types/tauri").MirrorTool[],
+) =>
+  invoke<import("@/types/tauri").MirrorSyncResult[]>("mirror_sync_tools", {
+    preview,
+    force,
+    tools,
+  });
+export const mirrorUnsyncTools = (tools?: import("@/types/tauri").MirrorTool[]) =>
+  invoke<import("@/types/tauri").MirrorSyncResult[]>("mirror_unsync_tools", {
+    tools,
+  });
+
+// ============================================================================
+// CUDA Commands
+// ============================================================================
+
+export const cudaListInstalled = () =>
+  invoke<import("@/types/tauri").CudaToolkit[]>("cuda_list_installed");
+export const cudaDetectCompatibility = (requestedVersion: string) =>
+  invoke<import("@/types/tauri").CudaCompatibility>(
+    "cuda_detect_compatibility",
+    { requestedVersion },
+  );
 
 // ============================================================================
 // Conda Environment Management Commands
@@ -4985,6 +6560,96 @@ export const condaChannelAdd = (channel: string) =>
   invoke<string>("conda_channel_add", { channel });
 export const condaChannelRemove = (channel: string) =>
   invoke<string>("conda_channel_remove", { channel });
+export const condaEnvDiskUsage = () =>
+  invoke<import("@/types/tauri").CondaDiskUsageReport>("conda_env_disk_usage");
+export const condaEnvPrunePreview = (env: string, allowBase?: boolean) =>
+  invoke<import("@/types/tauri").CondaPrunePreview>("conda_env_prune_preview", {
+    env,
+    allowBase,
+  });
+export const condaEnvPrune = (env: string, allowBase?: boolean) =>
+  invoke<import("@/types/tauri").CondaPruneResult>("conda_env_prune", {
+    env,
+    allowBase,
+  });
+export const condaEnvDiff = (envName: string, yamlPath: string) =>
+  invoke<import("@/types/tauri").CondaEnvDiff>("conda_env_diff", {
+    envName,
+    yamlPath,
+  });
+export const condaEnvSync = (
+  envName: string,
+  yamlPath: string,
+  prune: boolean = false,
+) =>
+  invoke<import("@/types/tauri").CondaEnvSyncResult>("conda_env_sync", {
+    envName,
+    yamlPath,
+    prune,
+  });
+export const condaLockInstall = (
+  lockfile: string,
+  envName: string,
+  platform?: string,
+) =>
+  invoke<import("@/types/tauri").CondaLockInstallResult>("conda_lock_install", {
+    lockfile,
+    envName,
+    platform,
+  });
+
+// ============================================================================
+// Pixi Project Management Commands
+// ============================================================================
+
+export const pixiProjectInit = (
+  path: string,
+  channels: string[] = [],
+  platforms: string[] = [],
+  pyproject: boolean = false,
+) => invoke<string>("pixi_project_init", { path, channels, platforms, pyproject });
+export const pixiAdd = (
+  manifestPath: string | null,
+  packages: string[],
+  pypi: boolean = false,
+  feature?: string,
+) => invoke<string>("pixi_add", { manifestPath, packages, pypi, feature });
+export const pixiRemove = (
+  manifestPath: string | null,
+  packages: string[],
+  pypi: boolean = false,
+  feature?: string,
+) => invoke<string>("pixi_remove", { manifestPath, packages, pypi, feature });
+export const pixiInstall = (
+  manifestPath: string | null,
+  environment?: string,
+  frozen: boolean = false,
+) => invoke<string>("pixi_install", { manifestPath, environment, frozen });
+export const pixiList = (manifestPath: string | null, environment?: string) =>
+  invoke<import("@/types/tauri").PixiPackage[]>("pixi_list", {
+    manifestPath,
+    environment,
+  });
+export const pixiEnvInfo = (manifestPath: string | null) =>
+  invoke<import("@/types/tauri").PixiInfo>("pixi_env_info", { manifestPath });
+export const pixiRun = (
+  manifestPath: string | null,
+  task: string,
+  runArgs: string[] = [],
+  environment?: string,
+) =>
+  invoke<import("@/types/tauri").PixiRunResult>("pixi_run", {
+    manifestPath,
+    task,
+    runArgs,
+    environment,
+  });
+export const pixiGlobalInstall = (packages: string[], channels: string[] = []) =>
+  invoke<string>("pixi_global_install", { packages, channels });
+export const pixiGlobalList = () =>
+  invoke<import("@/types/tauri").PixiGlobalEnv[]>("pixi_global_list");
+export const pixiGlobalRemove = (environments: string[]) =>
+  invoke<string>("pixi_global_remove", { environments });
 
 // ============================================================================
 // Poetry Project Management Commands
@@ -5014,6 +6679,35 @@ export const poetryCheck = (path: string) =>
   invoke<string>("poetry_check", { path });
 export const poetryVersion = (path: string) =>
   invoke<string>("poetry_version", { path });
+export const poetryAdd = (
+  path: string,
+  packageName: string,
+  options?: { group?: string; extras?: string[]; dev?: boolean },
+) =>
+  invoke<string>("poetry_add", {
+    path,
+    package: packageName,
+    group: options?.group,
+    extras: options?.extras,
+    dev: options?.dev,
+  });
+export const poetryRemove = (
+  path: string,
+  packageName: string,
+  group?: string,
+) => invoke<string>("poetry_remove", { path, package: packageName, group });
+/** Rejects with a `PoetryError` */
+export const poetryShow = (path: string, tree: boolean = false) =>
+  invoke<import("@/types/tauri").PoetryDependencyNode[]>("poetry_show", {
+    path,
+    tree,
+  });
+/** Rejects with a `PoetryError` */
+export const poetryGroupsList = (path: string) =>
+  invoke<import("@/types/tauri").PoetryDependencyGroup[]>(
+    "poetry_groups_list",
+    { path },
+  );
 
 // ============================================================================
 // pipx Commands
@@ -5033,6 +6727,34 @@ export const pipxEnsurepath = () => invoke<string>("pipx_ensurepath");
 export const pipxReinstallAll = () => invoke<string>("pipx_reinstall_all");
 export const pipxListJson = () => invoke<string>("pipx_list_json");
 
+// ============================================================================
+// Python Tool Commands (uv / pipx)
+// ============================================================================
+
+export const pythonToolInstall = (
+  name: string,
+  version?: string,
+  backend?: PythonToolBackendChoice,
+) =>
+  invoke<PythonToolInstallResult>("python_tool_install", {
+    name,
+    version,
+    backend,
+  });
+export const pythonToolList = () => invoke<PythonTool[]>("python_tool_list");
+export const pythonToolUpgrade = (name: string) =>
+  invoke<PythonToolInstallResult>("python_tool_upgrade", { name });
+export const pythonToolUninstall = (name: string) =>
+  invoke<void>("python_tool_uninstall", { name });
+export const pythonToolMigrate = (
+  backendFrom: PythonToolBackend,
+  backendTo: PythonToolBackend,
+) =>
+  invoke<PythonToolMigrateResult>("python_tool_migrate", {
+    backendFrom,
+    backendTo,
+  });
+
 // ============================================================================
 // Window Effect Commands
 // ============================================================================
@@ -10,6 +10,9 @@ declare module "extism:host" {
     // --- Configuration ---
     cognia_config_get(ptr: I64): I64;
     cognia_config_set(ptr: I64): I64;
+    cognia_config_get_typed(ptr: I64): I64;
+    cognia_config_set_typed(ptr: I64): I64;
+    cognia_config_all(ptr: I64): I64;
 
     // --- Environment ---
     cognia_env_list(ptr: I64): I64;
@@ -29,6 +32,8 @@ declare module "extism:host" {
     cognia_pkg_check_updates(ptr: I64): I64;
     cognia_pkg_install(ptr: I64): I64;
     cognia_pkg_uninstall(ptr: I64): I64;
+    cognia_pkg_install_start(ptr: I64): I64;
+    cognia_pkg_operation_status(ptr: I64): I64;
 
     // --- File System (sandboxed) ---
     cognia_fs_read(ptr: I64): I64;
@@ -38,6 +43,12 @@ declare module "extism:host" {
     cognia_fs_delete(ptr: I64): I64;
     cognia_fs_mkdir(ptr: I64): I64;
 
+    // --- Storage ---
+    cognia_storage_get(ptr: I64): I64;
+    cognia_storage_set(ptr: I64): I64;
+    cognia_storage_delete(ptr: I64): I64;
+    cognia_storage_list(ptr: I64): I64;
+
     // --- HTTP ---
     cognia_http_get(ptr: I64): I64;
     cognia_http_post(ptr: I64): I64;
@@ -64,6 +75,7 @@ declare module "extism:host" {
     cognia_get_locale(ptr: I64): I64;
     cognia_i18n_translate(ptr: I64): I64;
     cognia_i18n_get_all(ptr: I64): I64;
+    cognia_i18n_get_locales(ptr: I64): I64;
 
     // --- Platform ---
     cognia_platform_info(ptr: I64): I64;
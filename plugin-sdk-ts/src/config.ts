@@ -1,4 +1,5 @@
 import { callHost, callHostJson } from './host';
+import type { PluginSettingsSnapshot } from './types';
 
 /**
  * Read a configuration value by key.
@@ -19,3 +20,30 @@ export function get(key: string): string | null {
 export function set(key: string, value: string): void {
   callHost('cognia_config_set', JSON.stringify({ key, value }));
 }
+
+/**
+ * Read one of this plugin's declared settings.
+ * Falls back to the manifest default when nothing is stored; returns null when unset.
+ */
+export function getTyped<T = unknown>(key: string): T | null {
+  const result = callHostJson<{ value: T | null }>(
+    'cognia_config_get_typed',
+    JSON.stringify({ key }),
+  );
+  return result.value;
+}
+
+/**
+ * Write one of this plugin's declared settings.
+ * The host validates the value against the manifest schema.
+ */
+export function setTyped<T>(key: string, value: T): void {
+  callHost('cognia_config_set_typed', JSON.stringify({ key, value }));
+}
+
+/**
+ * Read all of this plugin's settings: declared defaults merged with stored values.
+ */
+export function all(): PluginSettingsSnapshot {
+  return callHostJson<PluginSettingsSnapshot>('cognia_config_all', '');
+}
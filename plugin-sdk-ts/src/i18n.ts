@@ -1,5 +1,5 @@
 import { callHostJson } from './host';
-import type { LocaleInfo } from './types';
+import type { LocaleInfo, LocaleResolution } from './types';
 
 /**
  * Get the current application locale (e.g. "en", "zh").
@@ -14,7 +14,8 @@ export function getLocale(): string {
 
 /**
  * Translate a key using the plugin's locale data.
- * Falls back: current locale -> "en" -> raw key.
+ * Falls back: current locale -> language only (e.g. "zh-TW" -> "zh") ->
+ * manifest `default_locale` (or "en") -> raw key.
  * Supports {param} interpolation.
  */
 export function translate(
@@ -41,3 +42,26 @@ export function t(key: string): string {
 export function getAll(): LocaleInfo {
   return callHostJson<LocaleInfo>('cognia_i18n_get_all', '');
 }
+
+/**
+ * Get how the app locale resolves against this plugin's locales.
+ */
+export function resolution(): LocaleResolution {
+  return callHostJson<LocaleResolution>('cognia_i18n_get_locales', '');
+}
+
+/**
+ * Get the plugin locale translations are served from (e.g. "zh" when the
+ * app runs in "zh-TW"), or the app locale when the plugin ships none.
+ */
+export function currentLocale(): string {
+  const result = resolution();
+  return result.resolved ?? result.locale;
+}
+
+/**
+ * Get the locales shipped by this plugin, sorted.
+ */
+export function availableLocales(): string[] {
+  return resolution().available;
+}
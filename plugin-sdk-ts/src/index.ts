@@ -42,6 +42,7 @@ export * as platform from './platform';
 export * as process from './process';
 export * as profiles from './profiles';
 export * as shell from './shell';
+export * as storage from './storage';
 export * as ui from './ui';
 export * as wsl from './wsl';
 
@@ -76,6 +77,7 @@ import * as platform from './platform';
 import * as process from './process';
 import * as profiles from './profiles';
 import * as shell from './shell';
+import * as storage from './storage';
 import * as ui from './ui';
 import * as wsl from './wsl';
 
@@ -100,6 +102,7 @@ export const cognia = {
   process,
   profiles,
   shell,
+  storage,
   ui,
   wsl,
 };
@@ -5,6 +5,7 @@ import type {
   InstalledPackage,
   PackageInfo,
   PackageSummary,
+  PkgOperation,
   UpdateInfo,
   VersionInfo,
 } from './types';
@@ -23,6 +24,17 @@ export function search(
   );
 }
 
+/** Event delivered to `cognia_on_event` while a `startInstall` operation runs. */
+export const INSTALL_PROGRESS_EVENT = 'pkg_install_progress';
+
+/**
+ * Search packages of one provider. Throws if the provider is unknown or disabled.
+ * Requires: pkg_search permission.
+ */
+export function searchIn(provider: string, query: string): PackageSummary[] {
+  return search(query, provider);
+}
+
 /**
  * Get detailed package info.
  * Requires: pkg_search permission.
@@ -130,3 +142,30 @@ export function uninstall(
     }),
   );
 }
+
+/**
+ * Start installing a package in the background and return the operation id.
+ * Progress arrives as `pkg_install_progress` events with a `PkgOperation` payload.
+ * Requires: pkg_install permission.
+ */
+export function startInstall(
+  provider: string,
+  name: string,
+  version?: string | null,
+): string {
+  const result = callHostJson<{ operationId: string }>(
+    'cognia_pkg_install_start',
+    JSON.stringify({ provider, name, version: version ?? null }),
+  );
+  return result.operationId;
+}
+
+/**
+ * Get the state of a package operation started by this plugin.
+ */
+export function operationStatus(id: string): PkgOperation {
+  return callHostJson<PkgOperation>(
+    'cognia_pkg_operation_status',
+    JSON.stringify({ id }),
+  );
+}
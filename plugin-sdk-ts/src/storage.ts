@@ -0,0 +1,149 @@
+import { callHost, callHostJson } from './host';
+import type { StorageEntry } from './types';
+
+/**
+ * Thrown by `set` / `setJson` when the write would exceed the plugin's
+ * storage quota.
+ */
+export class StorageQuotaExceededError extends Error {
+  readonly usageBytes: number;
+  readonly quotaBytes: number;
+  readonly requiredBytes: number;
+
+  constructor(
+    message: string,
+    usageBytes: number,
+    quotaBytes: number,
+    requiredBytes: number,
+  ) {
+    super(message);
+    this.name = 'StorageQuotaExceededError';
+    this.usageBytes = usageBytes;
+    this.quotaBytes = quotaBytes;
+    this.requiredBytes = requiredBytes;
+  }
+}
+
+type StorageSetResult =
+  | { ok: true }
+  | {
+      ok: false;
+      error: {
+        code: string;
+        message: string;
+        usageBytes?: number;
+        quotaBytes?: number;
+        requiredBytes?: number;
+      };
+    };
+
+function encodeHex(bytes: Uint8Array): string {
+  let out = '';
+  for (const byte of bytes) {
+    out += byte.toString(16).padStart(2, '0');
+  }
+  return out;
+}
+
+function decodeHex(text: string): Uint8Array {
+  const bytes = new Uint8Array(text.length / 2);
+  for (let i = 0; i < bytes.length; i++) {
+    bytes[i] = parseInt(text.slice(i * 2, i * 2 + 2), 16);
+  }
+  return bytes;
+}
+
+/**
+ * Read raw bytes stored under `key`. Returns null when the key is not stored.
+ */
+export function get(key: string): Uint8Array | null {
+  const result = callHostJson<{ value: string | null }>(
+    'cognia_storage_get',
+    JSON.stringify({ key }),
+  );
+  return result.value === null ? null : decodeHex(result.value);
+}
+
+/**
+ * Store raw bytes under `key`.
+ * Throws `StorageQuotaExceededError` when the plugin's store is full.
+ */
+export function set(key: string, value: Uint8Array): void {
+  const result = callHostJson<StorageSetResult>(
+    'cognia_storage_set',
+    JSON.stringify({ key, value: encodeHex(value) }),
+  );
+  if (result.ok) {
+    return;
+  }
+  const { error } = result;
+  if (error.code === 'quota_exceeded') {
+    throw new StorageQuotaExceededError(
+      error.message,
+      error.usageBytes ?? 0,
+      error.quotaBytes ?? 0,
+      error.requiredBytes ?? 0,
+    );
+  }
+  throw new Error(error.message);
+}
+
+/**
+ * Delete `key`. Returns whether it existed.
+ */
+export function remove(key: string): boolean {
+  const result = callHostJson<{ deleted: boolean }>(
+    'cognia_storage_delete',
+    JSON.stringify({ key }),
+  );
+  return result.deleted;
+}
+
+/**
+ * List stored keys starting with `prefix` (all keys when omitted).
+ */
+export function list(prefix = ''): StorageEntry[] {
+  return callHostJson<StorageEntry[]>(
+    'cognia_storage_list',
+    JSON.stringify({ prefix }),
+  );
+}
+
+/**
+ * Read a JSON value stored under `key`.
+ */
+export function getJson<T = unknown>(key: string): T | null {
+  const bytes = get(key);
+  return bytes === null ? null : (JSON.parse(utf8Decode(bytes)) as T);
+}
+
+/**
+ * Serialize `value` as JSON and store it under `key`.
+ */
+export function setJson<T>(key: string, value: T): void {
+  set(key, utf8Encode(JSON.stringify(value)));
+}
+
+// UTF-8 via URI escaping so the JSON layer does not depend on TextEncoder
+// being present in the plugin runtime.
+function utf8Encode(text: string): Uint8Array {
+  const escaped = encodeURIComponent(text);
+  const bytes: number[] = [];
+  for (let i = 0; i < escaped.length; i++) {
+    if (escaped[i] === '%') {
+      bytes.push(parseInt(escaped.slice(i + 1, i + 3), 16));
+      i += 2;
+    } else {
+      bytes.push(escaped.charCodeAt(i));
+    }
+  }
+  return Uint8Array.from(bytes);
+}
+
+function utf8Decode(bytes: Uint8Array): string {
+  let escaped = '';
+  for (const byte of bytes) {
+    escaped += `%${byte.toString(16).padStart(2, '0')}`;
+  }
+  return decodeURIComponent(escaped);
+}
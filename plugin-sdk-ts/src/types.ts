@@ -9,6 +9,18 @@ export interface PlatformInfo {
   osVersion: string;
 }
 
+// ============================================================================
+// Config
+// ============================================================================
+
+export interface PluginSettingsSnapshot {
+  values: Record<string, unknown>;
+  defaultedKeys: string[];
+  unknownKeys: string[];
+  invalidKeys: string[];
+  unknownValues: Record<string, unknown>;
+}
+
 // ============================================================================
 // Environment
 // ============================================================================
@@ -91,6 +103,36 @@ export interface InstallReceipt {
   provider: string;
 }
 
+export type PkgOperationStatus = 'running' | 'completed' | 'failed';
+
+/**
+ * State of a background install started with `pkg.startInstall`.
+ * Also the payload of `pkg_install_progress` events.
+ */
+export interface PkgOperation {
+  id: string;
+  pluginId: string;
+  provider: string;
+  name: string;
+  version: string | null;
+  status: PkgOperationStatus;
+  stage:
+    | 'fetching'
+    | 'downloading'
+    | 'extracting'
+    | 'configuring'
+    | 'post_install'
+    | 'done'
+    | 'failed'
+    | null;
+  progress: number;
+  message: string | null;
+  error: string | null;
+  receipt: InstallReceipt | null;
+  startedAt: string;
+  finishedAt: string | null;
+}
+
 // ============================================================================
 // File System
 // ============================================================================
@@ -106,6 +148,16 @@ export interface FileExistsResult {
   isDir: boolean;
 }
 
+// ============================================================================
+// Storage
+// ============================================================================
+
+export interface StorageEntry {
+  key: string;
+  sizeBytes: number;
+  modifiedAt: string | null;
+}
+
 // ============================================================================
 // HTTP
 // ============================================================================
@@ -174,6 +226,16 @@ export interface LocaleInfo {
   strings: Record<string, string>;
 }
 
+/** How the app locale resolves against the plugin's shipped locales. */
+export interface LocaleResolution {
+  /** Application locale (e.g. "zh-TW") */
+  locale: string;
+  /** Plugin locale strings resolve to (e.g. "zh"), null if none matches */
+  resolved: string | null;
+  available: string[];
+  defaultLocale: string;
+}
+
 // ============================================================================
 // Events
 // ============================================================================
@@ -31,6 +31,16 @@ import type {
   PluginUiRequestResult,
 } from './types';
 
+/**
+ * Version of the JSON produced by the `render*` functions. Version 2 added
+ * the `line-chart`, `sparkline` and `stat` blocks.
+ */
+export const UI_SCHEMA_VERSION = 2;
+/** Most points a chart series, sparkline or label list may hold */
+export const MAX_CHART_POINTS = 500;
+/** Most series a single line chart may hold */
+export const MAX_CHART_SERIES = 8;
+
 // ============================================================================
 // Block Types
 // ============================================================================
@@ -241,6 +251,44 @@ export interface UiArtifactActionsBlock {
   }[];
 }
 
+/** One named line of a `line-chart` block */
+export interface ChartSeries {
+  name: string;
+  values: number[];
+  color?: string;
+}
+
+/** Y-axis hints; the host picks a range from the data when unset */
+export interface ChartAxis {
+  min?: number;
+  max?: number;
+  unit?: string;
+}
+
+export interface UiLineChartBlock {
+  type: 'line-chart';
+  series: ChartSeries[];
+  /** X-axis labels, one per point */
+  labels?: string[];
+  yAxis?: ChartAxis;
+  /** Points the host keeps per series (most recent win) */
+  maxPoints?: number;
+  title?: string;
+}
+
+export interface UiSparklineBlock {
+  type: 'sparkline';
+  values: number[];
+}
+
+export interface UiStatBlock {
+  type: 'stat';
+  label: string;
+  value: string | number;
+  /** Change since the previous reading */
+  delta?: number;
+}
+
 export type UiLogStreamEntry = UiLogStreamBlock['entries'][number];
 export type UiArtifactAction = UiArtifactActionsBlock['artifacts'][number];
 
@@ -251,7 +299,8 @@ export type UiBlock =
   | UiFormBlock | UiActionsBlock | UiGroupBlock
   | UiTabsBlock | UiAccordionBlock | UiCopyButtonBlock | UiFileInputBlock
   | UiJsonViewBlock | UiDescriptionListBlock | UiStatCardsBlock | UiResultBlock
-  | UiConditionalGroupBlock | UiStepperBlock | UiLogStreamBlock | UiArtifactActionsBlock;
+  | UiConditionalGroupBlock | UiStepperBlock | UiLogStreamBlock | UiArtifactActionsBlock
+  | UiLineChartBlock | UiSparklineBlock | UiStatBlock;
 
 export interface UiOutputChannels {
   structured?: UiBlock[];
@@ -268,6 +317,8 @@ export interface UiOutputChannels {
 }
 
 export interface UiResponseEnvelope {
+  /** `UI_SCHEMA_VERSION` the payload was built against; absent on version 1 payloads */
+  schemaVersion?: number;
   ui?: UiBlock[];
   state?: Record<string, unknown>;
   outputChannels?: UiOutputChannels;
@@ -487,6 +538,70 @@ export function artifactActions(artifacts: UiArtifactAction[]): UiArtifactAction
   return { type: 'artifact-actions', artifacts };
 }
 
+function checkPoints(what: string, values: number[], limit: number): void {
+  if (values.length > limit) {
+    throw new Error(`${what} has ${values.length} points, more than the limit of ${limit}`);
+  }
+  if (values.some((v) => !Number.isFinite(v))) {
+    throw new Error(`${what} contains a non-finite value`);
+  }
+}
+
+export function chartSeries(name: string, values: number[], color?: string): ChartSeries {
+  const series: ChartSeries = { name, values };
+  if (color) series.color = color;
+  return series;
+}
+
+/**
+ * Time-series chart with one line per series. Throws if the chart exceeds
+ * `MAX_CHART_SERIES`, or a series or the labels exceed `maxPoints`
+ * (at most `MAX_CHART_POINTS`).
+ */
+export function lineChart(
+  series: ChartSeries[],
+  options?: {
+    labels?: string[];
+    yAxis?: ChartAxis;
+    maxPoints?: number;
+    title?: string;
+  },
+): UiLineChartBlock {
+  if (series.length > MAX_CHART_SERIES) {
+    throw new Error(
+      `line chart has ${series.length} series, more than the limit of ${MAX_CHART_SERIES}`,
+    );
+  }
+  const limit = Math.min(options?.maxPoints ?? MAX_CHART_POINTS, MAX_CHART_POINTS);
+  for (const s of series) {
+    checkPoints(`series '${s.name}'`, s.values, limit);
+  }
+  if (options?.labels && options.labels.length > limit) {
+    throw new Error(
+      `line chart has ${options.labels.length} labels, more than the limit of ${limit}`,
+    );
+  }
+  const block: UiLineChartBlock = { type: 'line-chart', series };
+  if (options?.labels) block.labels = options.labels;
+  if (options?.yAxis) block.yAxis = options.yAxis;
+  if (options?.maxPoints !== undefined) block.maxPoints = limit;
+  if (options?.title) block.title = options.title;
+  return block;
+}
+
+/** Compact trend line without axes. Throws past `MAX_CHART_POINTS` values. */
+export function sparkline(values: number[]): UiSparklineBlock {
+  checkPoints('sparkline', values, MAX_CHART_POINTS);
+  return { type: 'sparkline', values };
+}
+
+/** KPI tile; `delta` is the change since the previous reading */
+export function stat(label: string, value: string | number, delta?: number): UiStatBlock {
+  const block: UiStatBlock = { type: 'stat', label, value };
+  if (delta !== undefined) block.delta = delta;
+  return block;
+}
+
 export function numberField(
   id: string,
   label: string,
@@ -584,12 +699,12 @@ export function arrayField(
 // ============================================================================
 
 export function render(blocks: UiBlock[]): string {
-  const payload: UiResponseEnvelope = { ui: blocks };
+  const payload: UiResponseEnvelope = { schemaVersion: UI_SCHEMA_VERSION, ui: blocks };
   return JSON.stringify(payload);
 }
 
 export function renderWithState(blocks: UiBlock[], state: Record<string, unknown>): string {
-  const payload: UiResponseEnvelope = { ui: blocks, state };
+  const payload: UiResponseEnvelope = { schemaVersion: UI_SCHEMA_VERSION, ui: blocks, state };
   return JSON.stringify(payload);
 }
 
@@ -598,7 +713,11 @@ export function renderWithChannels(
   outputChannels: UiOutputChannels,
   state?: Record<string, unknown>,
 ): string {
-  const payload: UiResponseEnvelope = { ui: blocks, outputChannels };
+  const payload: UiResponseEnvelope = {
+    schemaVersion: UI_SCHEMA_VERSION,
+    ui: blocks,
+    outputChannels,
+  };
   if (state) {
     payload.state = state;
   }
@@ -1,4 +1,5 @@
 use crate::host;
+use crate::types::PluginSettingsSnapshot;
 use extism_pdk::*;
 
 /// Read a configuration value by key.
@@ -17,3 +18,35 @@ pub fn set(key: &str, value: &str) -> Result<(), Error> {
     }
     Ok(())
 }
+
+/// Read one of this plugin's declared settings and deserialize it into `T`.
+///
+/// Falls back to the manifest default when the setting has not been stored.
+/// Returns `None` when the setting is neither stored nor has a default.
+pub fn get_typed<T: serde::de::DeserializeOwned>(key: &str) -> Result<Option<T>, Error> {
+    let input = serde_json::json!({ "key": key }).to_string();
+    let result = unsafe { host::cognia_config_get_typed(input)? };
+    let parsed: serde_json::Value = serde_json::from_str(&result)?;
+    match &parsed["value"] {
+        serde_json::Value::Null => Ok(None),
+        value => Ok(Some(serde_json::from_value(value.clone())?)),
+    }
+}
+
+/// Write one of this plugin's declared settings.
+///
+/// The host validates the value against the manifest schema and rejects
+/// mismatches with an error describing the expected type.
+pub fn set_typed<T: serde::Serialize>(key: &str, value: &T) -> Result<(), Error> {
+    let input = serde_json::json!({ "key": key, "value": value }).to_string();
+    unsafe {
+        host::cognia_config_set_typed(input)?;
+    }
+    Ok(())
+}
+
+/// Read all of this plugin's settings: declared defaults merged with stored values.
+pub fn all() -> Result<PluginSettingsSnapshot, Error> {
+    let result = unsafe { host::cognia_config_all(String::new())? };
+    Ok(serde_json::from_str(&result)?)
+}
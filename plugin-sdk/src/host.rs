@@ -5,6 +5,9 @@
     // --- Config ---
     pub fn cognia_config_get(input: String) -> String;
     pub fn cognia_config_set(input: String) -> String;
+    pub fn cognia_config_get_typed(input: String) -> String;
+    pub fn cognia_config_set_typed(input: String) -> String;
+    pub fn cognia_config_all(input: String) -> String;
 
     // --- Environment ---
     pub fn cognia_env_list(input: String) -> String;
@@ -24,6 +27,8 @@
     pub fn cognia_pkg_check_updates(input: String) -> String;
     pub fn cognia_pkg_install(input: String) -> String;
     pub fn cognia_pkg_uninstall(input: String) -> String;
+    pub fn cognia_pkg_install_start(input: String) -> String;
+    pub fn cognia_pkg_operation_status(input: String) -> String;
 
     // --- Filesystem ---
     pub fn cognia_fs_read(input: String) -> String;
@@ -33,6 +38,12 @@
     pub fn cognia_fs_delete(input: String) -> String;
     pub fn cognia_fs_mkdir(input: String) -> String;
 
+    // --- Storage ---
+    pub fn cognia_storage_get(input: String) -> String;
+    pub fn cognia_storage_set(input: String) -> String;
+    pub fn cognia_storage_delete(input: String) -> String;
+    pub fn cognia_storage_list(input: String) -> String;
+
     // --- HTTP ---
     pub fn cognia_http_get(input: String) -> String;
     pub fn cognia_http_post(input: String) -> String;
@@ -59,6 +70,7 @@
     pub fn cognia_get_locale(input: String) -> String;
     pub fn cognia_i18n_translate(input: String) -> String;
     pub fn cognia_i18n_get_all(input: String) -> String;
+    pub fn cognia_i18n_get_locales(input: String) -> String;
 
     // --- Platform & Meta ---
     pub fn cognia_platform_info(input: String) -> String;
@@ -1,5 +1,5 @@
 use crate::host;
-use crate::types::LocaleInfo;
+use crate::types::{LocaleInfo, LocaleResolution};
 use extism_pdk::*;
 use std::collections::HashMap;
 
@@ -11,7 +11,8 @@ pub fn get_locale() -> Result<String, Error> {
 }
 
 /// Translate a key using the plugin's locale data.
-/// Falls back: current locale -> "en" -> raw key.
+/// Falls back: current locale -> language only (e.g. "zh-TW" -> "zh") ->
+/// manifest `default_locale` (or "en") -> raw key.
 /// Supports {param} interpolation.
 pub fn translate(key: &str, params: &[(&str, &str)]) -> Result<String, Error> {
     let params_map: HashMap<&str, &str> = params.iter().cloned().collect();
@@ -31,3 +32,21 @@ pub fn get_all() -> Result<LocaleInfo, Error> {
     let result = unsafe { host::cognia_i18n_get_all(String::new())? };
     Ok(serde_json::from_str(&result)?)
 }
+
+/// Get how the app locale resolves against this plugin's locales.
+pub fn resolution() -> Result<LocaleResolution, Error> {
+    let result = unsafe { host::cognia_i18n_get_locales(String::new())? };
+    Ok(serde_json::from_str(&result)?)
+}
+
+/// Get the plugin locale translations are served from (e.g. "zh" when the
+/// app runs in "zh-TW"), or the app locale when the plugin ships none.
+pub fn current_locale() -> Result<String, Error> {
+    let resolution = resolution()?;
+    Ok(resolution.resolved.unwrap_or(resolution.locale))
+}
+
+/// Get the locales shipped by this plugin, sorted.
+pub fn available_locales() -> Result<Vec<String>, Error> {
+    Ok(resolution()?.available)
+}
@@ -36,6 +36,7 @@
 pub mod process;
 pub mod profiles;
 pub mod shell;
+pub mod storage;
 pub mod types;
 pub mod ui;
 pub mod wsl;
@@ -63,6 +64,7 @@ pub mod cognia {
     pub use crate::process;
     pub use crate::profiles;
     pub use crate::shell;
+    pub use crate::storage;
     pub use crate::ui;
     pub use crate::wsl;
 }
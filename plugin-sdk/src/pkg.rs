@@ -2,6 +2,10 @@
 use crate::types::*;
 use extism_pdk::*;
 
+/// Event delivered to `cognia_on_event` while a `start_install` operation runs.
+/// The payload is a [`PkgOperation`].
+pub const INSTALL_PROGRESS_EVENT: &str = "pkg_install_progress";
+
 /// Search for packages.
 pub fn search(query: &str, provider: Option<&str>) -> Result<Vec<PackageSummary>, Error> {
     let input = serde_json::json!({ "query": query, "provider": provider }).to_string();
@@ -9,6 +13,11 @@ pub fn search(query: &str, provider: Option<&str>) -> Result<Vec<PackageSummary>
     Ok(serde_json::from_str(&result)?)
 }
 
+/// Search packages of one provider. Fails if the provider is unknown or disabled.
+pub fn search_in(provider: &str, query: &str) -> Result<Vec<PackageSummary>, Error> {
+    search(query, Some(provider))
+}
+
 /// Get detailed package info.
 pub fn info(name: &str, provider: Option<&str>) -> Result<PackageInfo, Error> {
     let input = serde_json::json!({ "name": name, "provider": provider }).to_string();
@@ -70,3 +79,21 @@ pub fn uninstall(name: &str, version: Option<&str>, provider: Option<&str>) -> R
     }
     Ok(())
 }
+
+/// Start installing a package in the background and return the operation id.
+/// Requires the `pkg_install` permission. Progress arrives as
+/// [`INSTALL_PROGRESS_EVENT`] events; poll with [`operation_status`].
+pub fn start_install(provider: &str, name: &str, version: Option<&str>) -> Result<String, Error> {
+    let input =
+        serde_json::json!({ "provider": provider, "name": name, "version": version }).to_string();
+    let result = unsafe { host::cognia_pkg_install_start(input)? };
+    let parsed: serde_json::Value = serde_json::from_str(&result)?;
+    Ok(parsed["operationId"].as_str().unwrap_or("").to_string())
+}
+
+/// Get the state of a package operation started by this plugin.
+pub fn operation_status(id: &str) -> Result<PkgOperation, Error> {
+    let input = serde_json::json!({ "id": id }).to_string();
+    let result = unsafe { host::cognia_pkg_operation_status(input)? };
+    Ok(serde_json::from_str(&result)?)
+}
@@ -0,0 +1,160 @@
+use crate::host;
+use crate::types::StorageEntry;
+use extism_pdk::*;
+use std::fmt;
+
+/// Typed errors reported by the storage host functions.
+///
+/// Returned inside the `Error` of [`set`] / [`set_json`]; recover it with
+/// `err.downcast_ref::<StorageError>()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageError {
+    /// The write would push the plugin's store over its quota.
+    QuotaExceeded {
+        usage_bytes: u64,
+        quota_bytes: u64,
+        required_bytes: u64,
+    },
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::QuotaExceeded {
+                usage_bytes,
+                quota_bytes,
+                required_bytes,
+            } => write!(
+                f,
+                "storage quota exceeded: writing {} bytes would exceed the {} byte quota ({} bytes used)",
+                required_bytes, quota_bytes, usage_bytes
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        out.push(DIGITS[(byte >> 4) as usize] as char);
+        out.push(DIGITS[(byte & 0x0f) as usize] as char);
+    }
+    out
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if text.len() % 2 != 0 {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn parse_set_result(result: &str) -> Result<(), Error> {
+    let parsed: serde_json::Value = serde_json::from_str(result)?;
+    if parsed["ok"].as_bool().unwrap_or(false) {
+        return Ok(());
+    }
+    let error = &parsed["error"];
+    match error["code"].as_str() {
+        Some("quota_exceeded") => Err(Error::new(StorageError::QuotaExceeded {
+            usage_bytes: error["usageBytes"].as_u64().unwrap_or(0),
+            quota_bytes: error["quotaBytes"].as_u64().unwrap_or(0),
+            required_bytes: error["requiredBytes"].as_u64().unwrap_or(0),
+        })),
+        _ => Err(Error::msg(
+            error["message"]
+                .as_str()
+                .unwrap_or("storage write failed")
+                .to_string(),
+        )),
+    }
+}
+
+/// Read raw bytes stored under `key`.
+pub fn get(key: &str) -> Result<Option<Vec<u8>>, Error> {
+    let input = serde_json::json!({ "key": key }).to_string();
+    let result = unsafe { host::cognia_storage_get(input)? };
+    let parsed: serde_json::Value = serde_json::from_str(&result)?;
+    match parsed["value"].as_str() {
+        Some(hex) => decode_hex(hex)
+            .map(Some)
+            .ok_or_else(|| Error::msg("host returned malformed storage value")),
+        None => Ok(None),
+    }
+}
+
+/// Store raw bytes under `key`.
+///
+/// Fails with [`StorageError::QuotaExceeded`] when the plugin's store is full.
+pub fn set(key: &str, value: &[u8]) -> Result<(), Error> {
+    let input = serde_json::json!({ "key": key, "value": encode_hex(value) }).to_string();
+    let result = unsafe { host::cognia_storage_set(input)? };
+    parse_set_result(&result)
+}
+
+/// Delete `key`. Returns whether it existed.
+pub fn delete(key: &str) -> Result<bool, Error> {
+    let input = serde_json::json!({ "key": key }).to_string();
+    let result = unsafe { host::cognia_storage_delete(input)? };
+    let parsed: serde_json::Value = serde_json::from_str(&result)?;
+    Ok(parsed["deleted"].as_bool().unwrap_or(false))
+}
+
+/// List stored keys starting with `prefix` (use `""` for all keys).
+pub fn list(prefix: &str) -> Result<Vec<StorageEntry>, Error> {
+    let input = serde_json::json!({ "prefix": prefix }).to_string();
+    let result = unsafe { host::cognia_storage_list(input)? };
+    Ok(serde_json::from_str(&result)?)
+}
+
+/// Read a JSON value stored under `key` and deserialize it into `T`.
+pub fn get_json<T: serde::de::DeserializeOwned>(key: &str) -> Result<Option<T>, Error> {
+    match get(key)? {
+        Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+        None => Ok(None),
+    }
+}
+
+/// Serialize `value` as JSON and store it under `key`.
+pub fn set_json<T: serde::Serialize>(key: &str, value: &T) -> Result<(), Error> {
+    set(key, &serde_json::to_vec(value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_roundtrip() {
+        let bytes = vec![0x00, 0x7f, 0x80, 0xff, 0x12];
+        let encoded = encode_hex(&bytes);
+        assert_eq!(encoded, "007f80ff12");
+        assert_eq!(decode_hex(&encoded), Some(bytes));
+        assert_eq!(decode_hex("abc"), None);
+        assert_eq!(decode_hex("zz"), None);
+    }
+
+    #[test]
+    fn test_parse_set_result_surfaces_quota_error() {
+        assert!(parse_set_result(r#"{"ok":true}"#).is_ok());
+
+        let err = parse_set_result(
+            r#"{"ok":false,"error":{"code":"quota_exceeded","message":"full","usageBytes":9,"quotaBytes":10,"requiredBytes":4}}"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<StorageError>(),
+            Some(&StorageError::QuotaExceeded {
+                usage_bytes: 9,
+                quota_bytes: 10,
+                required_bytes: 4,
+            })
+        );
+    }
+}
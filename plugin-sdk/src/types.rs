@@ -14,6 +14,30 @@ pub struct PlatformInfo {
     pub os_version: String,
 }
 
+// ============================================================================
+// Config
+// ============================================================================
+
+/// Plugin settings resolved against the manifest `[[settings]]` schema.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginSettingsSnapshot {
+    /// Declared settings with stored values or manifest defaults applied
+    pub values: HashMap<String, serde_json::Value>,
+    /// Keys whose value came from the manifest default
+    #[serde(default)]
+    pub defaulted_keys: Vec<String>,
+    /// Stored keys no longer declared by the current manifest
+    #[serde(default)]
+    pub unknown_keys: Vec<String>,
+    /// Stored values that no longer satisfy the schema
+    #[serde(default)]
+    pub invalid_keys: Vec<String>,
+    /// Raw stored values for `unknown_keys`
+    #[serde(default)]
+    pub unknown_values: HashMap<String, serde_json::Value>,
+}
+
 // ============================================================================
 // Environment
 // ============================================================================
@@ -105,6 +129,35 @@ pub struct InstallReceipt {
     pub provider: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PkgOperationStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+/// State of a background install started with `pkg::start_install`.
+/// Also the payload of `pkg_install_progress` events.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PkgOperation {
+    pub id: String,
+    pub plugin_id: String,
+    pub provider: String,
+    pub name: String,
+    pub version: Option<String>,
+    pub status: PkgOperationStatus,
+    /// fetching | downloading | extracting | configuring | post_install | done | failed
+    pub stage: Option<String>,
+    pub progress: f32,
+    pub message: Option<String>,
+    pub error: Option<String>,
+    pub receipt: Option<InstallReceipt>,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+}
+
 // ============================================================================
 // File System
 // ============================================================================
@@ -124,6 +177,19 @@ pub struct FileExistsResult {
     pub is_dir: bool,
 }
 
+// ============================================================================
+// Storage
+// ============================================================================
+
+/// A key in the plugin's key-value store.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageEntry {
+    pub key: String,
+    pub size_bytes: u64,
+    pub modified_at: Option<String>,
+}
+
 // ============================================================================
 // HTTP
 // ============================================================================
@@ -228,6 +294,18 @@ pub struct LocaleInfo {
     pub strings: HashMap<String, String>,
 }
 
+/// How the app locale resolves against the plugin's shipped locales.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocaleResolution {
+    /// Application locale (e.g. "zh-TW")
+    pub locale: String,
+    /// Plugin locale strings resolve to (e.g. "zh"), if any matches
+    pub resolved: Option<String>,
+    pub available: Vec<String>,
+    pub default_locale: String,
+}
+
 // ============================================================================
 // Events
 // ============================================================================
@@ -40,6 +40,14 @@
 
 use serde::{Deserialize, Serialize};
 
+/// Version of the JSON produced by the `render*` functions. Version 2 added
+/// the `line-chart`, `sparkline` and `stat` blocks.
+pub const UI_SCHEMA_VERSION: u32 = 2;
+/// Most points a chart series, sparkline or label list may hold
+pub const MAX_CHART_POINTS: usize = 500;
+/// Most series a single line chart may hold
+pub const MAX_CHART_SERIES: usize = 8;
+
 // ============================================================================
 // Block Types
 // ============================================================================
@@ -184,6 +192,27 @@ pub enum UiBlock {
     LogStream { entries: Vec<LogEntry> },
     #[serde(rename = "artifact-actions")]
     ArtifactActions { artifacts: Vec<ArtifactAction> },
+    #[serde(rename = "line-chart")]
+    LineChart {
+        series: Vec<ChartSeries>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        labels: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none", rename = "yAxis")]
+        y_axis: Option<ChartAxis>,
+        #[serde(skip_serializing_if = "Option::is_none", rename = "maxPoints")]
+        max_points: Option<usize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+    },
+    #[serde(rename = "sparkline")]
+    Sparkline { values: Vec<f64> },
+    #[serde(rename = "stat")]
+    Stat {
+        label: String,
+        value: serde_json::Value,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        delta: Option<f64>,
+    },
     #[serde(rename = "group")]
     Group {
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -257,6 +286,37 @@ pub struct ArtifactAction {
     pub action: Option<String>,
 }
 
+/// One named line of a `line-chart` block
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChartSeries {
+    pub name: String,
+    pub values: Vec<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+/// Y-axis hints; the host picks a range from the data when unset
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChartAxis {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+}
+
+/// Optional settings for [`line_chart`]
+#[derive(Debug, Clone, Default)]
+pub struct ChartOptions {
+    /// X-axis labels, one per point
+    pub labels: Option<Vec<String>>,
+    pub y_axis: Option<ChartAxis>,
+    /// Points the host keeps per series (most recent win); capped at [`MAX_CHART_POINTS`]
+    pub max_points: Option<usize>,
+    pub title: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum FormField {
@@ -450,6 +510,9 @@ pub struct UiOutputChannels {
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct UiResponseEnvelope {
+    /// [`UI_SCHEMA_VERSION`] the payload was built against; absent on version 1 payloads
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_version: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ui: Option<Vec<UiBlock>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -965,12 +1028,89 @@ pub fn artifact_actions(artifacts: Vec<ArtifactAction>) -> UiBlock {
     UiBlock::ArtifactActions { artifacts }
 }
 
+fn check_points(what: &str, values: &[f64], limit: usize) -> Result<(), Error> {
+    if values.len() > limit {
+        return Err(Error::msg(format!(
+            "{} has {} points, more than the limit of {}",
+            what,
+            values.len(),
+            limit
+        )));
+    }
+    if values.iter().any(|v| !v.is_finite()) {
+        return Err(Error::msg(format!("{} contains a non-finite value", what)));
+    }
+    Ok(())
+}
+
+pub fn chart_series(name: &str, values: &[f64], color: Option<&str>) -> ChartSeries {
+    ChartSeries {
+        name: name.to_string(),
+        values: values.to_vec(),
+        color: color.map(|v| v.to_string()),
+    }
+}
+
+/// Time-series chart with one line per series. Fails if the chart exceeds
+/// [`MAX_CHART_SERIES`], or a series or the labels exceed `max_points`
+/// (at most [`MAX_CHART_POINTS`]).
+pub fn line_chart(series: Vec<ChartSeries>, options: ChartOptions) -> Result<UiBlock, Error> {
+    if series.len() > MAX_CHART_SERIES {
+        return Err(Error::msg(format!(
+            "line chart has {} series, more than the limit of {}",
+            series.len(),
+            MAX_CHART_SERIES
+        )));
+    }
+    let limit = options
+        .max_points
+        .unwrap_or(MAX_CHART_POINTS)
+        .min(MAX_CHART_POINTS);
+    for s in &series {
+        check_points(&format!("series '{}'", s.name), &s.values, limit)?;
+    }
+    if let Some(labels) = &options.labels {
+        if labels.len() > limit {
+            return Err(Error::msg(format!(
+                "line chart has {} labels, more than the limit of {}",
+                labels.len(),
+                limit
+            )));
+        }
+    }
+    Ok(UiBlock::LineChart {
+        series,
+        labels: options.labels,
+        y_axis: options.y_axis,
+        max_points: options.max_points.map(|n| n.min(MAX_CHART_POINTS)),
+        title: options.title,
+    })
+}
+
+/// Compact trend line without axes. Fails past [`MAX_CHART_POINTS`] values.
+pub fn sparkline(values: &[f64]) -> Result<UiBlock, Error> {
+    check_points("sparkline", values, MAX_CHART_POINTS)?;
+    Ok(UiBlock::Sparkline {
+        values: values.to_vec(),
+    })
+}
+
+/// KPI tile; `delta` is the change since the previous reading
+pub fn stat(label: &str, value: serde_json::Value, delta: Option<f64>) -> UiBlock {
+    UiBlock::Stat {
+        label: label.to_string(),
+        value,
+        delta,
+    }
+}
+
 // ============================================================================
 // Render Functions
 // ============================================================================
 
 pub fn render(blocks: &[UiBlock]) -> String {
     let payload = UiResponseEnvelope {
+        schema_version: Some(UI_SCHEMA_VERSION),
         ui: Some(blocks.to_vec()),
         state: None,
         output_channels: None,
@@ -980,6 +1120,7 @@ pub fn render(blocks: &[UiBlock]) -> String {
 
 pub fn render_with_state(blocks: &[UiBlock], state: &serde_json::Value) -> String {
     let payload = UiResponseEnvelope {
+        schema_version: Some(UI_SCHEMA_VERSION),
         ui: Some(blocks.to_vec()),
         state: Some(state.clone()),
         output_channels: None,
@@ -993,6 +1134,7 @@ pub fn render_with_channels(
     state: Option<&serde_json::Value>,
 ) -> String {
     let payload = UiResponseEnvelope {
+        schema_version: Some(UI_SCHEMA_VERSION),
         ui: Some(blocks.to_vec()),
         state: state.cloned(),
         output_channels: Some(output_channels),
@@ -1397,4 +1539,69 @@ fn test_render_with_channels() {
         assert!(parsed["outputChannels"]["stream"].is_array());
         assert!(parsed["outputChannels"]["artifacts"].is_array());
     }
+
+    #[test]
+    fn test_render_is_stable_with_schema_version() {
+        let output = render(&[text("Hello", None), divider()]);
+        assert_eq!(
+            output,
+            r#"{"schemaVersion":2,"ui":[{"type":"text","content":"Hello"},{"type":"divider"}]}"#
+        );
+    }
+
+    #[test]
+    fn test_chart_blocks() {
+        let chart = line_chart(
+            vec![
+                chart_series("cpu", &[12.0, 30.5, 18.0], Some("#22c55e")),
+                chart_series("mem", &[40.0, 41.0, 43.0], None),
+            ],
+            ChartOptions {
+                labels: Some(vec!["t0".into(), "t1".into(), "t2".into()]),
+                y_axis: Some(ChartAxis {
+                    min: Some(0.0),
+                    max: Some(100.0),
+                    unit: Some("%".into()),
+                }),
+                max_points: Some(60),
+                title: None,
+            },
+        )
+        .unwrap();
+        let json = serde_json::to_value(&chart).unwrap();
+        assert_eq!(json["type"], "line-chart");
+        assert_eq!(json["series"][0]["values"][1], 30.5);
+        assert_eq!(json["yAxis"]["unit"], "%");
+        assert_eq!(json["maxPoints"], 60);
+        assert!(json.get("title").is_none());
+
+        let json = serde_json::to_value(sparkline(&[1.0, 2.0]).unwrap()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"type": "sparkline", "values": [1.0, 2.0]})
+        );
+
+        let json =
+            serde_json::to_value(stat("Speed", serde_json::json!("4 MB/s"), Some(-0.5))).unwrap();
+        assert_eq!(json["type"], "stat");
+        assert_eq!(json["delta"], -0.5);
+    }
+
+    #[test]
+    fn test_chart_size_limits() {
+        let too_long = vec![0.0; MAX_CHART_POINTS + 1];
+        assert!(sparkline(&too_long).is_err());
+        assert!(sparkline(&[1.0, f64::NAN]).is_err());
+
+        let series = vec![chart_series("a", &[1.0, 2.0, 3.0], None)];
+        let options = ChartOptions {
+            max_points: Some(2),
+            ..Default::default()
+        };
+        assert!(line_chart(series.clone(), options).is_err());
+        assert!(line_chart(series.clone(), ChartOptions::default()).is_ok());
+
+        let crowded = vec![series[0].clone(); MAX_CHART_SERIES + 1];
+        assert!(line_chart(crowded, ChartOptions::default()).is_err());
+    }
 }
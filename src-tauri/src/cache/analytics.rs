@@ -0,0 +1,602 @@
+//! Cleanup history analytics.
+//!
+//! Aggregates `CleanupHistory` records into weekly totals, a breakdown by
+//! category and the directories that needed cleaning most, and projects cache
+//! growth from the persisted cache size monitor samples. Results are computed
+//! on a blocking thread and memoized until the inputs change.
+
+use super::external::ExternalCacheProvider;
+use super::history::CleanupRecord;
+use super::sqlite_db::CacheUsageSample;
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::disk::format_size;
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, NaiveDate, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const TOP_DIRECTORIES: usize = 10;
+const ANALYTICS_CACHE_TTL: Duration = Duration::from_secs(600);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CleanupAnalyticsPeriod {
+    Month,
+    #[default]
+    Quarter,
+    Year,
+    All,
+}
+
+impl CleanupAnalyticsPeriod {
+    /// Length of the period in days; `None` covers the whole history
+    pub fn days(self) -> Option<u32> {
+        match self {
+            Self::Month => Some(30),
+            Self::Quarter => Some(90),
+            Self::Year => Some(365),
+            Self::All => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CleanupCategory {
+    ExpiredDownloads,
+    Metadata,
+    ExternalCaches,
+    FrameworkCaches,
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeeklyCleanup {
+    /// Monday of the week, as `YYYY-MM-DD`
+    pub week_start: String,
+    pub cleanups: usize,
+    pub freed_bytes: u64,
+    pub freed_human: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryCleanup {
+    pub category: CleanupCategory,
+    pub freed_bytes: u64,
+    pub freed_human: String,
+    pub file_count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectoryCleanup {
+    pub path: String,
+    pub freed_bytes: u64,
+    pub freed_human: String,
+    pub file_count: usize,
+    /// Cleanups that removed something from this directory
+    pub cleanups: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheGrowthProjection {
+    pub sample_count: usize,
+    /// Growth of the internal cache, from a least-squares fit of the samples
+    pub internal_bytes_per_day: f64,
+    /// Growth including external caches, when enough samples scanned them
+    pub total_bytes_per_day: Option<f64>,
+    pub current_size: u64,
+    pub max_size: u64,
+    /// Days until the internal cache reaches `max_size` at the current rate
+    pub days_until_max: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupAnalytics {
+    pub period: CleanupAnalyticsPeriod,
+    pub generated_at: String,
+    pub total_cleanups: usize,
+    pub total_freed_bytes: u64,
+    pub total_freed_human: String,
+    pub weekly: Vec<WeeklyCleanup>,
+    pub categories: Vec<CategoryCleanup>,
+    pub top_directories: Vec<DirectoryCleanup>,
+    /// `None` until at least two usage samples have been recorded
+    pub growth: Option<CacheGrowthProjection>,
+}
+
+fn is_framework_cache(provider_id: &str) -> bool {
+    ExternalCacheProvider::all()
+        .iter()
+        .any(|provider| provider.id() == provider_id && provider.category() == "terminal")
+}
+
+fn file_category(clean_type: &str, entry_type: &str) -> CleanupCategory {
+    match entry_type {
+        "download" | "default_download" | "partial" => CleanupCategory::ExpiredDownloads,
+        "metadata" => CleanupCategory::Metadata,
+        "external" => {
+            // External cleans are recorded as `<action>:<provider id>`
+            let provider_id = clean_type.rsplit(':').next().unwrap_or_default();
+            if is_framework_cache(provider_id) {
+                CleanupCategory::FrameworkCaches
+            } else {
+                CleanupCategory::ExternalCaches
+            }
+        }
+        _ => CleanupCategory::Other,
+    }
+}
+
+/// Directory a cleaned path is attributed to. External cache entries are
+/// directories themselves; synthetic `cache://` entries have none.
+fn cleaned_directory(path: &str, entry_type: &str) -> Option<String> {
+    if path.contains("://") {
+        return None;
+    }
+    if entry_type == "external" {
+        return Some(path.to_string());
+    }
+    Path::new(path)
+        .parent()
+        .map(|parent| parent.display().to_string())
+        .filter(|parent| !parent.is_empty())
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - ChronoDuration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Least-squares slope of `(days, bytes)` points, in bytes per day
+fn growth_rate(points: &[(f64, f64)]) -> Option<f64> {
+    if points.len() < 2 {
+        return None;
+    }
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    if variance <= f64::EPSILON {
+        return None;
+    }
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    Some(covariance / variance)
+}
+
+fn project_growth(samples: &[CacheUsageSample], max_size: u64) -> Option<CacheGrowthProjection> {
+    let parsed: Vec<(DateTime<Utc>, &CacheUsageSample)> = samples
+        .iter()
+        .filter_map(|sample| Some((sample.timestamp.parse().ok()?, sample)))
+        .collect();
+    let (origin, last) = (parsed.first()?.0, parsed.last()?.1);
+    let days = |timestamp: DateTime<Utc>| (timestamp - origin).num_seconds() as f64 / 86_400.0;
+
+    let internal: Vec<(f64, f64)> = parsed
+        .iter()
+        .map(|(timestamp, sample)| (days(*timestamp), sample.internal_size as f64))
+        .collect();
+    let internal_bytes_per_day = growth_rate(&internal)?;
+    let total: Vec<(f64, f64)> = parsed
+        .iter()
+        .filter(|(_, sample)| sample.external_size.is_some())
+        .map(|(timestamp, sample)| (days(*timestamp), sample.total_size as f64))
+        .collect();
+
+    let current_size = last.internal_size;
+    let days_until_max = if max_size == 0 {
+        None
+    } else if current_size >= max_size {
+        Some(0.0)
+    } else if internal_bytes_per_day > 0.0 {
+        Some((max_size - current_size) as f64 / internal_bytes_per_day)
+    } else {
+        None
+    };
+
+    Some(CacheGrowthProjection {
+        sample_count: parsed.len(),
+        internal_bytes_per_day,
+        total_bytes_per_day: growth_rate(&total),
+        current_size,
+        max_size,
+        days_until_max,
+    })
+}
+
+/// Aggregate `records` (most recent first, as stored) for `period`
+pub fn compute_analytics(
+    records: &[CleanupRecord],
+    samples: &[CacheUsageSample],
+    max_size: u64,
+    period: CleanupAnalyticsPeriod,
+    now: DateTime<Utc>,
+) -> CleanupAnalytics {
+    let since = period
+        .days()
+        .map(|days| now - ChronoDuration::days(days as i64));
+    let in_period: Vec<&CleanupRecord> = records
+        .iter()
+        .filter(|record| match since {
+            Some(since) => record.timestamp >= since,
+            None => true,
+        })
+        .collect();
+
+    // Weekly totals, with empty weeks filled in up to the current week
+    let mut weeks: HashMap<NaiveDate, (usize, u64)> = HashMap::new();
+    for record in &in_period {
+        let week = weeks
+            .entry(week_start(record.timestamp.date_naive()))
+            .or_default();
+        week.0 += 1;
+        week.1 += record.freed_bytes;
+    }
+    let current_week = week_start(now.date_naive());
+    let first_week = match since {
+        Some(since) => Some(week_start(since.date_naive())),
+        None => weeks.keys().min().copied(),
+    };
+    let mut weekly = Vec::new();
+    if let Some(mut week) = first_week {
+        while week <= current_week {
+            let (cleanups, freed_bytes) = weeks.get(&week).copied().unwrap_or_default();
+            weekly.push(WeeklyCleanup {
+                week_start: week.format("%Y-%m-%d").to_string(),
+                cleanups,
+                freed_bytes,
+                freed_human: format_size(freed_bytes),
+            });
+            week += ChronoDuration::days(7);
+        }
+    }
+
+    let mut categories: HashMap<CleanupCategory, (u64, usize)> = HashMap::new();
+    let mut directories: HashMap<String, (u64, usize, HashSet<&str>)> = HashMap::new();
+    for record in &in_period {
+        let mut record_bytes: HashMap<CleanupCategory, u64> = HashMap::new();
+        for file in &record.files {
+            let category = file_category(&record.clean_type, &file.entry_type);
+            let entry = categories.entry(category).or_default();
+            entry.0 += file.size;
+            entry.1 += 1;
+            *record_bytes.entry(category).or_default() += file.size;
+
+            if let Some(dir) = cleaned_directory(&file.path, &file.entry_type) {
+                let entry = directories.entry(dir).or_default();
+                entry.0 += file.size;
+                entry.1 += 1;
+                entry.2.insert(record.id.as_str());
+            }
+        }
+
+        // Files beyond the stored list count toward the record's main category
+        let listed: u64 = record_bytes.values().sum();
+        let unlisted = record.freed_bytes.saturating_sub(listed);
+        if unlisted > 0 {
+            let main = record_bytes
+                .iter()
+                .max_by_key(|(_, bytes)| **bytes)
+                .map(|(category, _)| *category)
+                .unwrap_or(CleanupCategory::Other);
+            let entry = categories.entry(main).or_default();
+            entry.0 += unlisted;
+            entry.1 += record.file_count.saturating_sub(record.files.len());
+        }
+    }
+
+    let mut categories: Vec<CategoryCleanup> = categories
+        .into_iter()
+        .filter(|(_, (bytes, _))| *bytes > 0)
+        .map(|(category, (freed_bytes, file_count))| CategoryCleanup {
+            category,
+            freed_bytes,
+            freed_human: format_size(freed_bytes),
+            file_count,
+        })
+        .collect();
+    categories.sort_by(|a, b| b.freed_bytes.cmp(&a.freed_bytes));
+
+    let mut top_directories: Vec<DirectoryCleanup> = directories
+        .into_iter()
+        .map(
+            |(path, (freed_bytes, file_count, cleanups))| DirectoryCleanup {
+                path,
+                freed_bytes,
+                freed_human: format_size(freed_bytes),
+                file_count,
+                cleanups: cleanups.len(),
+            },
+        )
+        .collect();
+    top_directories.sort_by(|a, b| {
+        b.freed_bytes
+            .cmp(&a.freed_bytes)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    top_directories.truncate(TOP_DIRECTORIES);
+
+    let total_freed_bytes = in_period.iter().map(|record| record.freed_bytes).sum();
+    CleanupAnalytics {
+        period,
+        generated_at: now.to_rfc3339(),
+        total_cleanups: in_period.len(),
+        total_freed_bytes,
+        total_freed_human: format_size(total_freed_bytes),
+        weekly,
+        categories,
+        top_directories,
+        growth: project_growth(samples, max_size),
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AnalyticsCacheEntry {
+    timestamp: Instant,
+    fingerprint: String,
+    analytics: CleanupAnalytics,
+}
+
+type AnalyticsCache = Mutex<HashMap<CleanupAnalyticsPeriod, AnalyticsCacheEntry>>;
+
+/// Memoized analytics per period, reused while the inputs are unchanged.
+static ANALYTICS_CACHE: Lazy<AnalyticsCache> = Lazy::new(AnalyticsCache::default);
+
+fn fingerprint(records: &[CleanupRecord], samples: &[CacheUsageSample], max_size: u64) -> String {
+    format!(
+        "{}:{}:{}:{}:{}",
+        records.len(),
+        records.first().map(|r| r.id.as_str()).unwrap_or_default(),
+        samples.len(),
+        samples
+            .last()
+            .map(|s| s.timestamp.as_str())
+            .unwrap_or_default(),
+        max_size
+    )
+}
+
+/// Analytics for `period`, computed on a blocking thread unless a cached
+/// result for the same inputs is still fresh
+pub async fn cleanup_analytics(
+    records: Vec<CleanupRecord>,
+    samples: Vec<CacheUsageSample>,
+    max_size: u64,
+    period: CleanupAnalyticsPeriod,
+) -> CogniaResult<CleanupAnalytics> {
+    cleanup_analytics_in(&ANALYTICS_CACHE, records, samples, max_size, period).await
+}
+
+async fn cleanup_analytics_in(
+    cache: &AnalyticsCache,
+    records: Vec<CleanupRecord>,
+    samples: Vec<CacheUsageSample>,
+    max_size: u64,
+    period: CleanupAnalyticsPeriod,
+) -> CogniaResult<CleanupAnalytics> {
+    let fingerprint = fingerprint(&records, &samples, max_size);
+    // Entries are replaced whole, so a poisoned map is still consistent
+    if let Some(entry) = cache.lock().unwrap_or_else(|e| e.into_inner()).get(&period) {
+        if entry.fingerprint == fingerprint && entry.timestamp.elapsed() < ANALYTICS_CACHE_TTL {
+            return Ok(entry.analytics.clone());
+        }
+    }
+
+    let analytics = tokio::task::spawn_blocking(move || {
+        compute_analytics(&records, &samples, max_size, period, Utc::now())
+    })
+    .await
+    .map_err(|e| CogniaError::Internal(format!("Cleanup analytics task failed: {}", e)))?;
+
+    cache.lock().unwrap_or_else(|e| e.into_inner()).insert(
+        period,
+        AnalyticsCacheEntry {
+            timestamp: Instant::now(),
+            fingerprint,
+            analytics: analytics.clone(),
+        },
+    );
+    Ok(analytics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CleanupRecordBuilder;
+
+    fn record(timestamp: &str, clean_type: &str, files: &[(&str, u64, &str)]) -> CleanupRecord {
+        let mut builder = CleanupRecordBuilder::new(clean_type, false);
+        for (path, size, entry_type) in files {
+            builder.add_file(*path, *size, *entry_type);
+        }
+        let mut record = builder.build();
+        record.timestamp = timestamp.parse().unwrap();
+        record
+    }
+
+    fn sample(timestamp: &str, internal: u64, external: Option<u64>) -> CacheUsageSample {
+        CacheUsageSample {
+            timestamp: timestamp.to_string(),
+            internal_size: internal,
+            external_size: external,
+            total_size: internal + external.unwrap_or(0),
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        "2026-03-18T12:00:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn test_weekly_totals_fill_empty_weeks() {
+        let records = vec![
+            record(
+                "2026-03-17T09:00:00Z",
+                "downloads",
+                &[("/c/d/a.bin", 300, "download")],
+            ),
+            record(
+                "2026-03-03T09:00:00Z",
+                "downloads",
+                &[("/c/d/b.bin", 100, "download")],
+            ),
+            record(
+                "2026-03-02T09:00:00Z",
+                "metadata",
+                &[("/c/m/x.json", 50, "metadata")],
+            ),
+            record(
+                "2025-01-01T09:00:00Z",
+                "downloads",
+                &[("/c/d/old.bin", 999, "download")],
+            ),
+        ];
+        let analytics = compute_analytics(&records, &[], 0, CleanupAnalyticsPeriod::Month, now());
+
+        assert_eq!(analytics.total_cleanups, 3);
+        assert_eq!(analytics.total_freed_bytes, 450);
+        let weeks: Vec<(&str, u64)> = analytics
+            .weekly
+            .iter()
+            .map(|w| (w.week_start.as_str(), w.freed_bytes))
+            .collect();
+        assert_eq!(
+            weeks,
+            vec![
+                ("2026-02-16", 0),
+                ("2026-02-23", 0),
+                ("2026-03-02", 150),
+                ("2026-03-09", 0),
+                ("2026-03-16", 300),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_categories_and_top_directories() {
+        let mut truncated = record(
+            "2026-03-10T09:00:00Z",
+            "all",
+            &[
+                ("/c/downloads/a.bin", 400, "download"),
+                ("/c/meta/a.json", 10, "metadata"),
+            ],
+        );
+        // 3 more download files were dropped from the stored list
+        truncated.freed_bytes += 600;
+        truncated.file_count += 3;
+        truncated.files_truncated = true;
+
+        let records = vec![
+            record(
+                "2026-03-12T09:00:00Z",
+                "external_force_clean:npm",
+                &[("/home/u/.npm/_cacache", 2000, "external")],
+            ),
+            record(
+                "2026-03-11T09:00:00Z",
+                "external_force_clean:starship",
+                &[("/home/u/.cache/starship", 70, "external")],
+            ),
+            truncated,
+            record(
+                "2026-03-09T09:00:00Z",
+                "auto_clean",
+                &[
+                    ("/c/downloads/b.bin", 100, "download"),
+                    ("cache://download-eviction/2", 5000, "download"),
+                    ("/c/quarantine/q.bin", 20, "quarantine"),
+                ],
+            ),
+        ];
+        let analytics = compute_analytics(&records, &[], 0, CleanupAnalyticsPeriod::Quarter, now());
+
+        let categories: Vec<(CleanupCategory, u64, usize)> = analytics
+            .categories
+            .iter()
+            .map(|c| (c.category, c.freed_bytes, c.file_count))
+            .collect();
+        assert_eq!(
+            categories,
+            vec![
+                (CleanupCategory::ExpiredDownloads, 6100, 6),
+                (CleanupCategory::ExternalCaches, 2000, 1),
+                (CleanupCategory::FrameworkCaches, 70, 1),
+                (CleanupCategory::Other, 20, 1),
+                (CleanupCategory::Metadata, 10, 1),
+            ]
+        );
+
+        let top = &analytics.top_directories;
+        assert_eq!(top[0].path, "/home/u/.npm/_cacache");
+        assert_eq!(top[1].path, "/c/downloads");
+        assert_eq!(top[1].freed_bytes, 500);
+        assert_eq!(top[1].cleanups, 2);
+        assert!(top.iter().all(|dir| !dir.path.contains("://")));
+    }
+
+    #[test]
+    fn test_growth_projection_from_samples() {
+        let gib = 1024 * 1024 * 1024;
+        let samples = vec![
+            sample("2026-03-01T00:00:00Z", gib, None),
+            sample("2026-03-02T00:00:00Z", gib + 100 * 1024 * 1024, Some(gib)),
+            sample("2026-03-03T00:00:00Z", gib + 200 * 1024 * 1024, Some(gib)),
+        ];
+        let growth = project_growth(&samples, 2 * gib).unwrap();
+
+        assert_eq!(growth.sample_count, 3);
+        assert!((growth.internal_bytes_per_day - 100.0 * 1024.0 * 1024.0).abs() < 1.0);
+        assert!((growth.total_bytes_per_day.unwrap() - 100.0 * 1024.0 * 1024.0).abs() < 1.0);
+        assert_eq!(growth.current_size, gib + 200 * 1024 * 1024);
+        let days = growth.days_until_max.unwrap();
+        assert!((days - 8.24).abs() < 0.01, "{}", days);
+
+        assert!(project_growth(&samples[..1], gib).is_none());
+        let shrinking = vec![
+            sample("2026-03-01T00:00:00Z", 500, None),
+            sample("2026-03-02T00:00:00Z", 400, None),
+        ];
+        let growth = project_growth(&shrinking, 1000).unwrap();
+        assert!(growth.internal_bytes_per_day < 0.0);
+        assert_eq!(growth.total_bytes_per_day, None);
+        assert_eq!(growth.days_until_max, None);
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_analytics_reuses_result_until_inputs_change() {
+        let cache = AnalyticsCache::default();
+        let records = vec![record(
+            "2026-03-17T09:00:00Z",
+            "downloads",
+            &[("/c/d/a.bin", 300, "download")],
+        )];
+        let analytics = |records: Vec<CleanupRecord>| {
+            cleanup_analytics_in(&cache, records, Vec::new(), 0, CleanupAnalyticsPeriod::All)
+        };
+        let first = analytics(records.clone()).await.unwrap();
+        let cached = analytics(records.clone()).await.unwrap();
+        assert_eq!(cached.generated_at, first.generated_at);
+
+        let mut more = records;
+        more.insert(
+            0,
+            record(
+                "2026-03-18T09:00:00Z",
+                "metadata",
+                &[("/c/m/a.json", 5, "metadata")],
+            ),
+        );
+        let refreshed = analytics(more).await.unwrap();
+        assert_eq!(refreshed.total_cleanups, 2);
+    }
+}
@@ -1,5 +1,8 @@
 use super::{
-    sqlite_db::{CacheAccessStats, CacheSizeSnapshot, DatabaseInfo, IntegrityCheckResult},
+    sqlite_db::{
+        CacheAccessStats, CacheReference, CacheSizeSnapshot, CacheUsageSample, DatabaseInfo,
+        IntegrityCheckResult, SourceAccessStats,
+    },
     CacheEntry, CacheEntryType, SharedCacheDb, SqliteCacheDb,
 };
 use crate::error::{CogniaError, CogniaResult};
@@ -10,7 +13,7 @@
 };
 use chrono::{Duration as ChronoDuration, Utc};
 use sha2::{Digest, Sha256};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
@@ -32,6 +35,15 @@ pub fn source_identity(url: &str, provider: Option<&str>) -> String {
         format!("{}|{}", provider.unwrap_or_default(), url)
     }
 
+    /// Provider a source identity was built for, used to tag cache metrics
+    fn access_source(source_identity: &str) -> &str {
+        source_identity
+            .split_once('|')
+            .map(|(provider, _)| provider)
+            .filter(|provider| !provider.is_empty())
+            .unwrap_or("download")
+    }
+
     pub async fn open(cache_dir: &Path) -> CogniaResult<Self> {
         let downloads_dir = cache_dir.join("downloads");
         fs::create_dir_all(&downloads_dir).await?;
@@ -72,7 +84,7 @@ pub async fn get_by_checksum_and_source(
             let key = Self::source_cache_key(checksum, source_identity);
             return Ok(self
                 .db
-                .get(&key)
+                .get_for_source(&key, Self::access_source(source_identity))
                 .await?
                 .filter(|entry| !entry.is_expired())
                 .map(|entry| entry.file_path.clone()));
@@ -80,17 +92,14 @@ pub async fn get_by_checksum_and_source(
         self.get_by_checksum(checksum).await
     }
 
-    pub async fn purge_checksum_entries(&mut self, checksum: &str) -> CogniaResult<()> {
+    /// Delete the cached file at `path` and every index entry pointing at it.
+    /// Other files recorded under the same checksum are left alone.
+    pub async fn purge_file_entries(&mut self, path: &Path) -> CogniaResult<()> {
         let entries = self.db.list_by_type(CacheEntryType::Download).await?;
-        let mut removed_paths = HashSet::new();
-
-        for entry in entries
-            .into_iter()
-            .filter(|entry| entry.checksum == checksum)
-        {
-            if removed_paths.insert(entry.file_path.clone()) && fs::exists(&entry.file_path).await {
-                let _ = fs::remove_file(&entry.file_path).await;
-            }
+        if fs::exists(path).await {
+            let _ = fs::remove_file(path).await;
+        }
+        for entry in entries.into_iter().filter(|entry| entry.file_path == path) {
             self.db.remove(&entry.key).await?;
         }
 
@@ -120,13 +129,13 @@ pub async fn get_validated_cache_hit(
         };
 
         if !fs::exists(&path).await {
-            self.purge_checksum_entries(checksum).await?;
+            self.purge_file_entries(&path).await?;
             return Ok(None);
         }
 
         let actual_checksum = fs::calculate_sha256(&path).await?;
         if actual_checksum != checksum {
-            self.purge_checksum_entries(checksum).await?;
+            self.purge_file_entries(&path).await?;
             return Err(CogniaError::ChecksumMismatch {
                 expected: checksum.to_string(),
                 actual: actual_checksum,
@@ -274,6 +283,23 @@ pub async fn add_file_with_source(
         Ok(target_path)
     }
 
+    /// Index a file that is already in place under the given keys, e.g. one
+    /// restored from quarantine
+    pub async fn register_file(
+        &mut self,
+        keys: &[String],
+        path: &Path,
+        checksum: &str,
+    ) -> CogniaResult<()> {
+        let size = fs::file_size(path).await?;
+        for key in keys {
+            let entry =
+                CacheEntry::new(key.clone(), path, size, checksum, CacheEntryType::Download);
+            self.db.insert(entry).await?;
+        }
+        Ok(())
+    }
+
     pub async fn verify(&mut self, checksum: &str) -> CogniaResult<bool> {
         if let Some(path) = self.get_by_checksum(checksum).await? {
             if fs::exists(&path).await {
@@ -286,12 +312,18 @@ pub async fn verify(&mut self, checksum: &str) -> CogniaResult<bool> {
 
     pub async fn remove(&mut self, checksum: &str) -> CogniaResult<bool> {
         let entries = self.db.list_by_type(CacheEntryType::Download).await?;
-        let exists = entries.iter().any(|entry| entry.checksum == checksum);
-        if !exists {
+        let paths: HashSet<PathBuf> = entries
+            .into_iter()
+            .filter(|entry| entry.checksum == checksum)
+            .map(|entry| entry.file_path)
+            .collect();
+        if paths.is_empty() {
             return Ok(false);
         }
 
-        self.purge_checksum_entries(checksum).await?;
+        for path in paths {
+            self.purge_file_entries(&path).await?;
+        }
         Ok(true)
     }
 
@@ -299,13 +331,18 @@ pub async fn clean(&mut self) -> CogniaResult<u64> {
         self.clean_with_option(false).await
     }
 
-    /// Clean all download cache entries with option to use trash
+    /// Clean all download cache entries with option to use trash.
+    /// Entries referenced by installed environments are kept.
     pub async fn clean_with_option(&mut self, use_trash: bool) -> CogniaResult<u64> {
         let mut total_freed = 0u64;
 
         let entries = self.db.list_by_type(CacheEntryType::Download).await?;
+        let referenced = self.db.referenced_checksums().await?;
 
         for entry in entries {
+            if referenced.contains(&entry.checksum) {
+                continue;
+            }
             if fs::exists(&entry.file_path).await {
                 total_freed += entry.size;
                 fs::remove_file_with_option(&entry.file_path, use_trash).await?;
@@ -334,7 +371,8 @@ pub async fn clean_expired(&mut self, max_age: Duration) -> CogniaResult<u64> {
         self.clean_expired_with_option(max_age, false).await
     }
 
-    /// Clean expired download entries with option to use trash
+    /// Clean expired download entries with option to use trash.
+    /// Entries referenced by installed environments are kept.
     pub async fn clean_expired_with_option(
         &mut self,
         max_age: Duration,
@@ -346,9 +384,13 @@ pub async fn clean_expired_with_option(
             .db
             .list_by_type_before(CacheEntryType::Download, cutoff)
             .await?;
+        let referenced = self.db.referenced_checksums().await?;
         let mut total_freed = 0u64;
 
         for entry in entries {
+            if referenced.contains(&entry.checksum) {
+                continue;
+            }
             if fs::exists(&entry.file_path).await {
                 total_freed += entry.size;
                 fs::remove_file_with_option(&entry.file_path, use_trash).await?;
@@ -360,20 +402,54 @@ pub async fn clean_expired_with_option(
     }
 
     pub async fn evict_to_size(&mut self, max_size: u64) -> CogniaResult<usize> {
+        self.evict_to_size_with_hard_limit(max_size, max_size).await
+    }
+
+    /// Evict least recently used downloads until the cache is at most `target`
+    /// bytes. Entries referenced by installed environments are only evicted
+    /// when the cache is still above `hard_limit` after every unreferenced
+    /// entry has been removed.
+    pub async fn evict_to_size_with_hard_limit(
+        &mut self,
+        target: u64,
+        hard_limit: u64,
+    ) -> CogniaResult<usize> {
         let (total_download_size, _, _) = self.db.stats_by_type(CacheEntryType::Download).await?;
-        if total_download_size <= max_size {
+        if total_download_size <= target {
             return Ok(0);
         }
 
-        let entries = self
-            .db
-            .list_by_type_lru(CacheEntryType::Download, 100)
-            .await?;
+        // Consider every entry so referenced ones at the head of the LRU order
+        // cannot hide unreferenced candidates further back
+        let mut entries = self.db.list_by_type(CacheEntryType::Download).await?;
+        entries.sort_by_key(|entry| entry.last_accessed.unwrap_or(entry.created_at));
+        let referenced = self.db.referenced_checksums().await?;
+        let (protected, unreferenced): (Vec<_>, Vec<_>) = entries
+            .into_iter()
+            .partition(|entry| referenced.contains(&entry.checksum));
+
         let mut remaining = total_download_size;
-        let mut removed = 0usize;
+        let mut removed = self
+            .evict_entries(unreferenced, target, &mut remaining)
+            .await?;
+        if remaining > hard_limit {
+            removed += self
+                .evict_entries(protected, hard_limit, &mut remaining)
+                .await?;
+        }
+
+        Ok(removed)
+    }
 
+    async fn evict_entries(
+        &mut self,
+        entries: Vec<CacheEntry>,
+        limit: u64,
+        remaining: &mut u64,
+    ) -> CogniaResult<usize> {
+        let mut removed = 0usize;
         for entry in entries {
-            if remaining <= max_size {
+            if *remaining <= limit {
                 break;
             }
 
@@ -382,13 +458,42 @@ pub async fn evict_to_size(&mut self, max_size: u64) -> CogniaResult<usize> {
             }
             if self.db.remove(&entry.key).await? {
                 removed += 1;
-                remaining = remaining.saturating_sub(entry.size);
+                *remaining = remaining.saturating_sub(entry.size);
             }
         }
-
         Ok(removed)
     }
 
+    /// Record that `env_type@version` was installed from the cached artifact
+    /// with `checksum`, protecting it from eviction and cleanup
+    pub async fn add_reference(
+        &self,
+        checksum: &str,
+        env_type: &str,
+        version: &str,
+    ) -> CogniaResult<()> {
+        self.db.add_reference(checksum, env_type, version).await
+    }
+
+    /// Drop the references held by an uninstalled `env_type@version`
+    pub async fn release_references(&self, env_type: &str, version: &str) -> CogniaResult<usize> {
+        self.db.remove_references(env_type, version).await
+    }
+
+    /// Environment references grouped by the checksum they protect
+    pub async fn references_by_checksum(
+        &self,
+    ) -> CogniaResult<HashMap<String, Vec<CacheReference>>> {
+        let mut grouped: HashMap<String, Vec<CacheReference>> = HashMap::new();
+        for reference in self.db.list_references().await? {
+            grouped
+                .entry(reference.checksum.clone())
+                .or_default()
+                .push(reference);
+        }
+        Ok(grouped)
+    }
+
     pub async fn stats(&self) -> CogniaResult<DownloadCacheStats> {
         let (total_size, entry_count, _) = self.db.stats_by_type(CacheEntryType::Download).await?;
 
@@ -406,6 +511,11 @@ pub fn get_access_stats(&self) -> CacheAccessStats {
         self.db.get_access_stats()
     }
 
+    /// Get hit/miss counters grouped by provider or cache namespace
+    pub async fn access_stats_by_source(&self) -> CogniaResult<Vec<SourceAccessStats>> {
+        self.db.access_stats_by_source().await
+    }
+
     /// Reset cache access statistics
     pub async fn reset_access_stats(&self) -> CogniaResult<()> {
         self.db.reset_access_stats().await
@@ -500,6 +610,76 @@ pub async fn get_size_snapshots(&self, days: u32) -> CogniaResult<Vec<CacheSizeS
     pub async fn prune_old_snapshots(&self, max_age_days: u32) -> CogniaResult<usize> {
         self.db.prune_old_snapshots(max_age_days).await
     }
+
+    /// Persist a cache size monitor sample
+    pub async fn record_usage_sample(
+        &self,
+        internal_size: u64,
+        external_size: Option<u64>,
+        total_size: u64,
+    ) -> CogniaResult<()> {
+        self.db
+            .record_usage_sample(internal_size, external_size, total_size)
+            .await
+    }
+
+    /// Get cache size monitor samples for the last N days
+    pub async fn get_usage_samples(&self, days: u32) -> CogniaResult<Vec<CacheUsageSample>> {
+        self.db.get_usage_samples(days).await
+    }
+}
+
+/// Cached install archive for a provider that downloads its own artifacts,
+/// re-verified against `checksum` before it is reused
+pub async fn cached_env_artifact(cache_dir: &Path, checksum: &str) -> Option<PathBuf> {
+    let mut cache = DownloadCache::open(cache_dir).await.ok()?;
+    match cache.get_validated_cache_hit(checksum, None).await {
+        Ok(path) => path,
+        Err(e) => {
+            log::warn!("Discarded cached install artifact {}: {}", checksum, e);
+            None
+        }
+    }
+}
+
+/// Keep a verified install archive in the download cache. A failure only
+/// costs a re-download later, so it is logged rather than returned.
+pub async fn store_env_artifact(cache_dir: &Path, artifact: &Path, checksum: &str, url: &str) {
+    let source_identity = DownloadCache::source_identity(url, None);
+    let result = match DownloadCache::open(cache_dir).await {
+        Ok(mut cache) => {
+            cache
+                .add_file_with_source(artifact, checksum, Some(&source_identity))
+                .await
+        }
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        log::warn!("Failed to cache install artifact {}: {}", checksum, e);
+    }
+}
+
+/// Record that `env_type@version` was installed from the cached archive with
+/// `checksum`, so eviction keeps it while the version is installed
+pub async fn reference_env_artifact(
+    cache_dir: &Path,
+    checksum: &str,
+    env_type: &str,
+    version: &str,
+) {
+    let result = match DownloadCache::open(cache_dir).await {
+        Ok(cache) => cache.add_reference(checksum, env_type, version).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        log::warn!(
+            "Failed to record cache reference {}@{} -> {}: {}",
+            env_type,
+            version,
+            checksum,
+            e
+        );
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -709,6 +889,76 @@ async fn test_evict_to_size() {
         assert!(stats_after.total_size <= target_size);
     }
 
+    #[tokio::test]
+    async fn test_evict_skips_referenced_entries_until_hard_limit() {
+        let dir = tempdir().unwrap();
+        let mut cache = DownloadCache::open(dir.path()).await.unwrap();
+
+        let mut checksums = Vec::new();
+        for i in 0..3 {
+            let test_file = dir.path().join(format!("ref-evict-{}.txt", i));
+            fs::write_file_string(&test_file, &format!("{}{}", i, "x".repeat(99)))
+                .await
+                .unwrap();
+            let checksum = fs::calculate_sha256(&test_file).await.unwrap();
+            cache.add_file(&test_file, &checksum).await.unwrap();
+            checksums.push(checksum);
+        }
+        // The oldest entry backs an installed version.
+        cache
+            .add_reference(&checksums[0], "node", "20.11.0")
+            .await
+            .unwrap();
+
+        // Soft target below total, hard limit still satisfied: only unreferenced go.
+        let evicted = cache.evict_to_size_with_hard_limit(0, 100).await.unwrap();
+        assert_eq!(evicted, 2);
+        assert!(cache
+            .get_by_checksum(&checksums[0])
+            .await
+            .unwrap()
+            .is_some());
+
+        // Over the hard limit with nothing else left: the referenced entry goes too.
+        let evicted = cache.evict_to_size_with_hard_limit(0, 0).await.unwrap();
+        assert_eq!(evicted, 1);
+        assert!(cache
+            .get_by_checksum(&checksums[0])
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_clean_keeps_referenced_until_released() {
+        let dir = tempdir().unwrap();
+        let mut cache = DownloadCache::open(dir.path()).await.unwrap();
+
+        let test_file = dir.path().join("ref-clean.txt");
+        fs::write_file_string(&test_file, "referenced content")
+            .await
+            .unwrap();
+        let checksum = fs::calculate_sha256(&test_file).await.unwrap();
+        cache.add_file(&test_file, &checksum).await.unwrap();
+        cache
+            .add_reference(&checksum, "python", "3.12.1")
+            .await
+            .unwrap();
+
+        let references = cache.references_by_checksum().await.unwrap();
+        assert_eq!(references[&checksum][0].label(), "python@3.12.1");
+
+        assert_eq!(cache.clean().await.unwrap(), 0);
+        assert_eq!(cache.stats().await.unwrap().entry_count, 1);
+
+        assert_eq!(
+            cache.release_references("python", "3.12.1").await.unwrap(),
+            1
+        );
+        assert!(cache.clean().await.unwrap() > 0);
+        assert_eq!(cache.stats().await.unwrap().entry_count, 0);
+    }
+
     #[tokio::test]
     async fn test_stats() {
         let dir = tempdir().unwrap();
@@ -1142,6 +1392,29 @@ async fn test_source_aware_cache_lookup_and_binding() {
         assert!(after_bind.is_some());
     }
 
+    #[tokio::test]
+    async fn test_source_lookups_tag_access_stats_by_provider() {
+        let dir = tempdir().unwrap();
+        let cache = DownloadCache::open(dir.path()).await.unwrap();
+
+        let with_provider =
+            DownloadCache::source_identity("https://example.com/a.zip", Some("npm"));
+        let without_provider = DownloadCache::source_identity("https://example.com/b.zip", None);
+        let _ = cache
+            .get_by_checksum_and_source("missing", Some(&with_provider))
+            .await
+            .unwrap();
+        let _ = cache
+            .get_by_checksum_and_source("missing", Some(&without_provider))
+            .await
+            .unwrap();
+
+        let by_source = cache.access_stats_by_source().await.unwrap();
+        let sources: Vec<&str> = by_source.iter().map(|s| s.source.as_str()).collect();
+        assert_eq!(sources, vec!["download", "npm"]);
+        assert!(by_source.iter().all(|s| s.misses == 1 && s.hits == 0));
+    }
+
     #[tokio::test]
     async fn test_corrupted_cache_entry_is_purged() {
         let dir = tempdir().unwrap();
@@ -1166,4 +1439,30 @@ async fn test_corrupted_cache_entry_is_purged() {
         assert!(cache.get_by_checksum(&checksum).await.unwrap().is_none());
         assert!(!fs::exists(&cached_path).await);
     }
+
+    #[tokio::test]
+    async fn test_purge_keeps_other_files_with_the_same_checksum() {
+        let dir = tempdir().unwrap();
+        let mut cache = DownloadCache::open(dir.path()).await.unwrap();
+
+        let test_file = dir.path().join("shared.txt");
+        fs::write_file_string(&test_file, "shared content")
+            .await
+            .unwrap();
+        let checksum = fs::calculate_sha256(&test_file).await.unwrap();
+        let cached_path = cache.add_file(&test_file, &checksum).await.unwrap();
+        cache
+            .register_file(&["restored:shared".to_string()], &test_file, &checksum)
+            .await
+            .unwrap();
+
+        cache.purge_file_entries(&cached_path).await.unwrap();
+
+        assert!(!fs::exists(&cached_path).await);
+        assert!(fs::exists(&test_file).await);
+        assert_eq!(
+            cache.get_by_checksum(&checksum).await.unwrap(),
+            Some(test_file.clone())
+        );
+    }
 }
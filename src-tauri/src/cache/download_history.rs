@@ -1,19 +1,42 @@
 //! Download history tracking
 
-use crate::download::{ArtifactProfile, InstallIntent, SourceDescriptor};
+use crate::download::{ArtifactProfile, DownloadTask, InstallIntent, SourceDescriptor};
 use crate::error::{CogniaError, CogniaResult};
 use crate::platform::{
     disk::{format_duration, format_size},
     fs,
 };
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
 
-/// Maximum number of history records to keep
+/// Hard upper bound on stored history records, regardless of retention settings
 const MAX_HISTORY_RECORDS: usize = 1000;
 
+/// Records kept by the default retention policy
+pub const DEFAULT_HISTORY_MAX_RECORDS: usize = MAX_HISTORY_RECORDS;
+
+/// Column order used for CSV export and expected by CSV import
+const CSV_COLUMNS: &[&str] = &[
+    "id",
+    "url",
+    "filename",
+    "destination",
+    "size",
+    "checksum",
+    "startedAt",
+    "completedAt",
+    "durationSecs",
+    "averageSpeed",
+    "status",
+    "error",
+    "provider",
+];
+
+/// Days of bandwidth usage buckets to keep (a little over a year)
+const MAX_USAGE_DAYS: i64 = 400;
+
 /// A single download history record
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -210,12 +233,17 @@ pub struct DownloadHistory {
     file_path: PathBuf,
     /// History records (newest first)
     records: VecDeque<DownloadRecord>,
+    /// Bandwidth usage ledger file path
+    usage_path: PathBuf,
+    /// Daily bandwidth usage, kept independently of the record cap
+    usage: UsageLedger,
 }
 
 impl DownloadHistory {
     /// Open or create a download history
     pub async fn open(cache_dir: &Path) -> CogniaResult<Self> {
         let file_path = cache_dir.join("download_history.json");
+        let usage_path = cache_dir.join("download_usage.json");
 
         let records = if fs::exists(&file_path).await {
             let content = fs::read_file_string(&file_path).await?;
@@ -224,7 +252,19 @@ pub async fn open(cache_dir: &Path) -> CogniaResult<Self> {
             VecDeque::new()
         };
 
-        Ok(Self { file_path, records })
+        let usage = if fs::exists(&usage_path).await {
+            let content = fs::read_file_string(&usage_path).await?;
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            UsageLedger::default()
+        };
+
+        Ok(Self {
+            file_path,
+            records,
+            usage_path,
+            usage,
+        })
     }
 
     /// Add a new record to the history
@@ -353,6 +393,268 @@ pub fn stats(&self) -> HistoryStats {
         stats
     }
 
+    /// Account transferred bytes for a provider/host on the sample's day
+    pub async fn record_usage(&mut self, sample: DownloadUsageSample) -> CogniaResult<()> {
+        let date = sample.recorded_at.date_naive();
+        let index =
+            match self.usage.buckets.iter().position(|b| {
+                b.date == date && b.provider == sample.provider && b.host == sample.host
+            }) {
+                Some(index) => index,
+                None => {
+                    self.usage.buckets.push(DownloadUsageBucket {
+                        date,
+                        provider: sample.provider.clone(),
+                        host: sample.host.clone(),
+                        network_bytes: 0,
+                        cache_hit_bytes: 0,
+                        download_count: 0,
+                        cache_hit_count: 0,
+                    });
+                    self.usage.buckets.len() - 1
+                }
+            };
+        let bucket = &mut self.usage.buckets[index];
+
+        if sample.from_cache {
+            bucket.cache_hit_bytes += sample.bytes;
+            bucket.cache_hit_count += 1;
+        } else {
+            bucket.network_bytes += sample.bytes;
+            bucket.download_count += 1;
+        }
+
+        let cutoff = Utc::now().date_naive() - Duration::days(MAX_USAGE_DAYS);
+        self.usage.buckets.retain(|b| b.date >= cutoff);
+
+        self.save_usage().await
+    }
+
+    /// Aggregate usage for a period, grouped by provider, day and host
+    pub fn usage_stats(&self, period: UsagePeriod, now: DateTime<Utc>) -> DownloadUsageStats {
+        let since = period.since(now.date_naive());
+        let buckets: Vec<&DownloadUsageBucket> = self
+            .usage
+            .buckets
+            .iter()
+            .filter(|b| since.map_or(true, |since| b.date >= since))
+            .collect();
+
+        let group = |key: &dyn Fn(&DownloadUsageBucket) -> String| -> Vec<UsageTotal> {
+            let mut totals: BTreeMap<String, UsageTotal> = BTreeMap::new();
+            for bucket in &buckets {
+                let k = key(*bucket);
+                totals
+                    .entry(k.clone())
+                    .or_insert_with(|| UsageTotal {
+                        key: k,
+                        ..Default::default()
+                    })
+                    .add(bucket);
+            }
+            totals.into_values().collect()
+        };
+
+        let mut by_provider = group(&|b| b.provider.clone());
+        let mut by_host = group(&|b| b.host.clone());
+        let by_day = group(&|b| b.date.to_string());
+        by_provider.sort_by(|a, b| b.network_bytes.cmp(&a.network_bytes));
+        by_host.sort_by(|a, b| b.network_bytes.cmp(&a.network_bytes));
+
+        let mut total = UsageTotal::default();
+        for bucket in &buckets {
+            total.add(bucket);
+        }
+
+        DownloadUsageStats {
+            period,
+            since: since.map(|d| d.to_string()),
+            total_network_bytes: total.network_bytes,
+            total_network_human: format_size(total.network_bytes),
+            total_cache_hit_bytes: total.cache_hit_bytes,
+            total_saved_human: format_size(total.cache_hit_bytes),
+            download_count: total.download_count,
+            cache_hit_count: total.cache_hit_count,
+            by_provider,
+            by_day,
+            by_host,
+        }
+    }
+
+    /// Network bytes downloaded in the calendar month containing `now`
+    pub fn month_network_bytes(&self, now: DateTime<Utc>) -> u64 {
+        let since = UsagePeriod::Month.since(now.date_naive());
+        self.usage
+            .buckets
+            .iter()
+            .filter(|b| since.map_or(true, |since| b.date >= since))
+            .map(|b| b.network_bytes)
+            .sum()
+    }
+
+    /// Returns this month's total once per month when it exceeds `cap_bytes`.
+    /// A cap of 0 disables the check.
+    pub async fn take_cap_exceeded(
+        &mut self,
+        cap_bytes: u64,
+        now: DateTime<Utc>,
+    ) -> CogniaResult<Option<u64>> {
+        if cap_bytes == 0 {
+            return Ok(None);
+        }
+        let month = now.format("%Y-%m").to_string();
+        if self.usage.cap_notified_month.as_deref() == Some(month.as_str()) {
+            return Ok(None);
+        }
+        let used = self.month_network_bytes(now);
+        if used <= cap_bytes {
+            return Ok(None);
+        }
+        self.usage.cap_notified_month = Some(month);
+        self.save_usage().await?;
+        Ok(Some(used))
+    }
+
+    /// Clear all bandwidth usage data, returning the number of buckets removed
+    pub async fn reset_usage(&mut self) -> CogniaResult<usize> {
+        let count = self.usage.buckets.len();
+        self.usage = UsageLedger::default();
+        self.save_usage().await?;
+        Ok(count)
+    }
+
+    async fn save_usage(&self) -> CogniaResult<()> {
+        fs::atomic_write_json(&self.usage_path, &self.usage, false).await?;
+        Ok(())
+    }
+
+    /// Drop records that fall outside the retention policy
+    pub async fn enforce_retention(
+        &mut self,
+        retention: HistoryRetention,
+        now: DateTime<Utc>,
+    ) -> CogniaResult<usize> {
+        let removed = self.apply_retention(retention, now);
+
+        if removed > 0 {
+            self.save().await?;
+        }
+
+        Ok(removed)
+    }
+
+    /// Drop records outside `retention` in memory, returning how many went
+    fn apply_retention(&mut self, retention: HistoryRetention, now: DateTime<Utc>) -> usize {
+        let len_before = self.records.len();
+        if retention.max_age_days > 0 {
+            let cutoff = now - Duration::days(retention.max_age_days as i64);
+            self.records.retain(|r| r.completed_at >= cutoff);
+        }
+        self.records
+            .truncate(retention.max_records.clamp(1, MAX_HISTORY_RECORDS));
+        len_before - self.records.len()
+    }
+
+    /// Write records matching `filter` to `path`, returning the number written.
+    /// The file is replaced atomically, so a failed export never leaves a
+    /// truncated file behind.
+    pub async fn export(
+        &self,
+        format: HistoryExportFormat,
+        path: &Path,
+        filter: &HistoryFilter,
+    ) -> CogniaResult<usize> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).await?;
+        }
+
+        let mut content = match format {
+            HistoryExportFormat::Csv => format!("{}\n", CSV_COLUMNS.join(",")),
+            HistoryExportFormat::Json => "[".to_string(),
+        };
+
+        let mut count = 0;
+        for record in self.records.iter().filter(|r| filter.matches(r)) {
+            match format {
+                HistoryExportFormat::Csv => {
+                    content.push_str(&record_to_csv_row(record));
+                    content.push('\n');
+                }
+                HistoryExportFormat::Json => {
+                    let json = serde_json::to_string(record)
+                        .map_err(|e| CogniaError::Internal(e.to_string()))?;
+                    let separator = if count == 0 { "" } else { "," };
+                    content.push_str(&format!("{}\n  {}", separator, json));
+                }
+            }
+            count += 1;
+        }
+
+        if format == HistoryExportFormat::Json {
+            content.push_str(if count == 0 { "]\n" } else { "\n]\n" });
+        }
+        fs::write_file_atomic(path, content.as_bytes()).await?;
+
+        Ok(count)
+    }
+
+    /// Merge records from a previous export, skipping IDs already present.
+    /// The merged history is trimmed to `retention`, so imported records
+    /// outside it are counted as skipped.
+    pub async fn import(
+        &mut self,
+        path: &Path,
+        format: Option<HistoryExportFormat>,
+        retention: HistoryRetention,
+        now: DateTime<Utc>,
+    ) -> CogniaResult<HistoryImportResult> {
+        let format = format
+            .or_else(|| HistoryExportFormat::from_path(path))
+            .ok_or_else(|| {
+                CogniaError::Config(
+                    "Cannot infer history import format; expected a .csv or .json file".into(),
+                )
+            })?;
+        let content = fs::read_file_string(path).await?;
+        let incoming = match format {
+            HistoryExportFormat::Json => {
+                serde_json::from_str::<Vec<DownloadRecord>>(content.trim_start_matches('\u{feff}'))
+                    .map_err(|e| CogniaError::Parse(format!("Invalid history JSON: {}", e)))?
+            }
+            HistoryExportFormat::Csv => parse_history_csv(&content)?,
+        };
+
+        let total = incoming.len();
+        let mut known: HashSet<String> = self.records.iter().map(|r| r.id.clone()).collect();
+        let mut added = HashSet::new();
+        for record in incoming {
+            if known.insert(record.id.clone()) {
+                added.insert(record.id.clone());
+                self.records.push_back(record);
+            }
+        }
+
+        let mut imported = 0;
+        if !added.is_empty() {
+            self.records
+                .make_contiguous()
+                .sort_by(|a, b| b.completed_at.cmp(&a.completed_at));
+            self.apply_retention(retention, now);
+            imported = self
+                .records
+                .iter()
+                .filter(|r| added.contains(&r.id))
+                .count();
+            self.save().await?;
+        }
+
+        Ok(HistoryImportResult {
+            imported,
+            skipped: total - imported,
+            total,
+        })
+    }
+
     /// Get record count
     pub fn len(&self) -> usize {
         self.records.len()
@@ -364,6 +666,252 @@ pub fn is_empty(&self) -> bool {
     }
 }
 
+/// Retention limits for download history records
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryRetention {
+    /// Maximum record age in days (0 = no age limit)
+    pub max_age_days: u32,
+    /// Maximum number of records to keep
+    pub max_records: usize,
+}
+
+impl Default for HistoryRetention {
+    fn default() -> Self {
+        Self {
+            max_age_days: 0,
+            max_records: DEFAULT_HISTORY_MAX_RECORDS,
+        }
+    }
+}
+
+/// File format for history export and import
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryExportFormat {
+    Csv,
+    Json,
+}
+
+impl HistoryExportFormat {
+    /// Infer the format from a file extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        match ext.as_str() {
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Filter applied when exporting history
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HistoryFilter {
+    /// Only include these statuses (empty = all)
+    pub status: Vec<DownloadStatus>,
+    /// Only include records completed at or after this time
+    pub from: Option<DateTime<Utc>>,
+    /// Only include records completed at or before this time
+    pub to: Option<DateTime<Utc>>,
+    /// Only include records from this provider (matches `github` for `github:owner/repo`)
+    pub provider: Option<String>,
+}
+
+impl HistoryFilter {
+    pub fn matches(&self, record: &DownloadRecord) -> bool {
+        if !self.status.is_empty() && !self.status.contains(&record.status) {
+            return false;
+        }
+        if self.from.is_some_and(|from| record.completed_at < from) {
+            return false;
+        }
+        if self.to.is_some_and(|to| record.completed_at > to) {
+            return false;
+        }
+        if let Some(provider) = self.provider.as_deref().map(str::trim) {
+            if provider.is_empty() {
+                return true;
+            }
+            let Some(record_provider) = record.provider.as_deref() else {
+                return false;
+            };
+            let base = record_provider.split(':').next().unwrap_or(record_provider);
+            return record_provider.eq_ignore_ascii_case(provider)
+                || base.eq_ignore_ascii_case(provider);
+        }
+        true
+    }
+}
+
+/// Outcome of importing a history export
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryImportResult {
+    pub imported: usize,
+    /// Records skipped because their ID was already present or they fall
+    /// outside the retention policy
+    pub skipped: usize,
+    pub total: usize,
+}
+
+fn status_str(status: &DownloadStatus) -> &'static str {
+    match status {
+        DownloadStatus::Completed => "completed",
+        DownloadStatus::Failed => "failed",
+        DownloadStatus::Cancelled => "cancelled",
+    }
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains(|c| matches!(c, ',' | '"' | '\n' | '\r')) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn record_to_csv_row(record: &DownloadRecord) -> String {
+    let fields = [
+        record.id.clone(),
+        record.url.clone(),
+        record.filename.clone(),
+        record.destination.display().to_string(),
+        record.size.to_string(),
+        record.checksum.clone().unwrap_or_default(),
+        record.started_at.to_rfc3339(),
+        record.completed_at.to_rfc3339(),
+        record.duration_secs.to_string(),
+        record.average_speed.to_string(),
+        status_str(&record.status).to_string(),
+        record.error.clone().unwrap_or_default(),
+        record.provider.clone().unwrap_or_default(),
+    ];
+    fields
+        .iter()
+        .map(|f| csv_field(f.as_str()))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Split CSV content into rows of fields, honouring quoted fields that span
+/// delimiters, escaped quotes and line breaks
+fn parse_csv_rows(content: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' => in_quotes = true,
+            ',' => row.push(std::mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    rows
+}
+
+fn parse_history_csv(content: &str) -> CogniaResult<Vec<DownloadRecord>> {
+    let mut rows = parse_csv_rows(content.trim_start_matches('\u{feff}')).into_iter();
+    let header = rows
+        .next()
+        .ok_or_else(|| CogniaError::Parse("History CSV is empty".into()))?;
+    let columns: HashMap<&str, usize> = header
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (name.trim(), i))
+        .collect();
+    let col = |row: &[String], name: &str| -> Option<String> {
+        columns
+            .get(name)
+            .and_then(|&i| row.get(i))
+            .filter(|v| !v.is_empty())
+            .cloned()
+    };
+    let parse_time = |value: Option<String>| {
+        value
+            .and_then(|v| DateTime::parse_from_rfc3339(&v).ok())
+            .map(|t| t.with_timezone(&Utc))
+    };
+
+    let mut records = Vec::new();
+    for (idx, row) in rows.enumerate() {
+        if row.iter().all(|f| f.is_empty()) {
+            continue;
+        }
+        let row_number = idx + 2;
+        let missing = |name: &str| {
+            CogniaError::Parse(format!("History CSV row {}: missing {}", row_number, name))
+        };
+
+        let url = col(&row, "url").ok_or_else(|| missing("url"))?;
+        let completed_at =
+            parse_time(col(&row, "completedAt")).ok_or_else(|| missing("completedAt"))?;
+        let status = match col(&row, "status").as_deref() {
+            None | Some("completed") => DownloadStatus::Completed,
+            Some("failed") => DownloadStatus::Failed,
+            Some("cancelled") => DownloadStatus::Cancelled,
+            Some(other) => {
+                return Err(CogniaError::Parse(format!(
+                    "History CSV row {}: unknown status '{}'",
+                    row_number, other
+                )))
+            }
+        };
+
+        records.push(DownloadRecord {
+            id: col(&row, "id").unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+            filename: col(&row, "filename")
+                .unwrap_or_else(|| url.rsplit('/').next().unwrap_or_default().to_string()),
+            url,
+            destination: PathBuf::from(col(&row, "destination").unwrap_or_default()),
+            size: col(&row, "size").and_then(|v| v.parse().ok()).unwrap_or(0),
+            checksum: col(&row, "checksum"),
+            started_at: parse_time(col(&row, "startedAt")).unwrap_or(completed_at),
+            completed_at,
+            duration_secs: col(&row, "durationSecs")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            average_speed: col(&row, "averageSpeed")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0.0),
+            status,
+            error: col(&row, "error"),
+            provider: col(&row, "provider"),
+            metadata: std::collections::HashMap::new(),
+            install_intent: None,
+            source_descriptor: None,
+            artifact_profile: None,
+        });
+    }
+
+    Ok(records)
+}
+
 /// Download history statistics
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -394,6 +942,138 @@ pub fn success_rate(&self) -> f32 {
     }
 }
 
+/// Bytes transferred for one provider/host on one day
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadUsageBucket {
+    pub date: NaiveDate,
+    pub provider: String,
+    pub host: String,
+    pub network_bytes: u64,
+    pub cache_hit_bytes: u64,
+    pub download_count: u64,
+    pub cache_hit_count: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct UsageLedger {
+    buckets: Vec<DownloadUsageBucket>,
+    /// `YYYY-MM` of the last month a soft-cap notification was raised
+    cap_notified_month: Option<String>,
+}
+
+/// A single accounting event produced when a download reaches a terminal
+/// state or is served from the download cache
+#[derive(Debug, Clone)]
+pub struct DownloadUsageSample {
+    pub provider: String,
+    pub host: String,
+    pub bytes: u64,
+    pub from_cache: bool,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl DownloadUsageSample {
+    /// Bytes fetched over the network
+    pub fn network(url: &str, provider: Option<&str>, bytes: u64) -> Self {
+        Self::new(url, provider, bytes, false)
+    }
+
+    /// Bytes served from `DownloadCache` instead of the network
+    pub fn cache_hit(url: &str, provider: Option<&str>, bytes: u64) -> Self {
+        Self::new(url, provider, bytes, true)
+    }
+
+    /// `received_bytes` of network usage by a task, attributed to the URL
+    /// (primary or mirror) it was served from
+    pub fn from_task(task: &DownloadTask, received_bytes: u64) -> Self {
+        let provider = task.usage_provider();
+        Self::network(task.effective_url(), Some(&provider), received_bytes)
+    }
+
+    fn new(url: &str, provider: Option<&str>, bytes: u64, from_cache: bool) -> Self {
+        let host = reqwest::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| h.to_lowercase()))
+            .unwrap_or_else(|| "unknown".to_string());
+        let provider = provider
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .unwrap_or("direct")
+            .to_string();
+        Self {
+            provider,
+            host,
+            bytes,
+            from_cache,
+            recorded_at: Utc::now(),
+        }
+    }
+}
+
+/// Reporting window for bandwidth usage
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UsagePeriod {
+    Day,
+    Week,
+    #[default]
+    Month,
+    Year,
+    All,
+}
+
+impl UsagePeriod {
+    /// First day included in the period, or `None` for all time
+    fn since(self, today: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            Self::Day => Some(today),
+            Self::Week => Some(today - Duration::days(6)),
+            Self::Month => today.with_day(1),
+            Self::Year => NaiveDate::from_ymd_opt(today.year(), 1, 1),
+            Self::All => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageTotal {
+    pub key: String,
+    pub network_bytes: u64,
+    pub cache_hit_bytes: u64,
+    pub download_count: u64,
+    pub cache_hit_count: u64,
+}
+
+impl UsageTotal {
+    fn add(&mut self, bucket: &DownloadUsageBucket) {
+        self.network_bytes += bucket.network_bytes;
+        self.cache_hit_bytes += bucket.cache_hit_bytes;
+        self.download_count += bucket.download_count;
+        self.cache_hit_count += bucket.cache_hit_count;
+    }
+}
+
+/// Bandwidth usage statistics for a period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadUsageStats {
+    pub period: UsagePeriod,
+    pub since: Option<String>,
+    pub total_network_bytes: u64,
+    pub total_network_human: String,
+    /// Bytes served from the download cache instead of the network
+    pub total_cache_hit_bytes: u64,
+    pub total_saved_human: String,
+    pub download_count: u64,
+    pub cache_hit_count: u64,
+    pub by_provider: Vec<UsageTotal>,
+    pub by_day: Vec<UsageTotal>,
+    pub by_host: Vec<UsageTotal>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -830,7 +1510,7 @@ async fn test_download_history_max_records() {
                 .unwrap();
         }
 
-        assert!(history.len() <= MAX_HISTORY_RECORDS);
+        assert_eq!(history.len(), MAX_HISTORY_RECORDS);
     }
 
     #[tokio::test]
@@ -877,4 +1557,294 @@ async fn test_download_history_persists_install_context() {
         assert!(persisted.source_descriptor.is_some());
         assert!(persisted.artifact_profile.is_some());
     }
+
+    #[tokio::test]
+    async fn test_usage_accounting_groups_and_persists() {
+        let temp_dir = tempdir().unwrap();
+        let mut history = DownloadHistory::open(temp_dir.path()).await.unwrap();
+
+        history
+            .record_usage(DownloadUsageSample::network(
+                "https://registry.npmmirror.com/a.tgz",
+                Some("npm"),
+                1000,
+            ))
+            .await
+            .unwrap();
+        history
+            .record_usage(DownloadUsageSample::network(
+                "https://github.com/o/r/releases/download/v1/x.zip",
+                Some("github"),
+                5000,
+            ))
+            .await
+            .unwrap();
+        history
+            .record_usage(DownloadUsageSample::cache_hit(
+                "https://github.com/o/r/releases/download/v1/x.zip",
+                Some("github"),
+                5000,
+            ))
+            .await
+            .unwrap();
+
+        let reopened = DownloadHistory::open(temp_dir.path()).await.unwrap();
+        let stats = reopened.usage_stats(UsagePeriod::Month, Utc::now());
+        assert_eq!(stats.total_network_bytes, 6000);
+        assert_eq!(stats.total_cache_hit_bytes, 5000);
+        assert_eq!(stats.download_count, 2);
+        assert_eq!(stats.cache_hit_count, 1);
+        assert_eq!(stats.by_provider[0].key, "github");
+        assert_eq!(stats.by_provider[0].network_bytes, 5000);
+        assert!(stats
+            .by_host
+            .iter()
+            .any(|h| h.key == "registry.npmmirror.com"));
+        assert_eq!(stats.by_day.len(), 1);
+        // Download history records are untouched by usage accounting
+        assert!(reopened.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_usage_cap_notifies_once_per_month_and_reset() {
+        let temp_dir = tempdir().unwrap();
+        let mut history = DownloadHistory::open(temp_dir.path()).await.unwrap();
+        history
+            .record_usage(DownloadUsageSample::network("not a url", None, 2048))
+            .await
+            .unwrap();
+
+        let now = Utc::now();
+        assert_eq!(history.take_cap_exceeded(0, now).await.unwrap(), None);
+        assert_eq!(history.take_cap_exceeded(4096, now).await.unwrap(), None);
+        assert_eq!(
+            history.take_cap_exceeded(1024, now).await.unwrap(),
+            Some(2048)
+        );
+        assert_eq!(history.take_cap_exceeded(1024, now).await.unwrap(), None);
+
+        let stats = history.usage_stats(UsagePeriod::All, now);
+        assert_eq!(stats.by_host[0].key, "unknown");
+        assert_eq!(stats.by_provider[0].key, "direct");
+
+        assert_eq!(history.reset_usage().await.unwrap(), 1);
+        assert_eq!(history.month_network_bytes(now), 0);
+    }
+
+    #[test]
+    fn test_usage_period_since() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 15).unwrap();
+        assert_eq!(UsagePeriod::Day.since(today), Some(today));
+        assert_eq!(
+            UsagePeriod::Week.since(today),
+            NaiveDate::from_ymd_opt(2026, 3, 9)
+        );
+        assert_eq!(
+            UsagePeriod::Month.since(today),
+            NaiveDate::from_ymd_opt(2026, 3, 1)
+        );
+        assert_eq!(
+            UsagePeriod::Year.since(today),
+            NaiveDate::from_ymd_opt(2026, 1, 1)
+        );
+        assert_eq!(UsagePeriod::All.since(today), None);
+    }
+
+    fn record_at(name: &str, provider: Option<&str>, days_ago: i64) -> DownloadRecord {
+        let mut record = DownloadRecord::completed(
+            format!("https://example.com/{}", name),
+            name.to_string(),
+            PathBuf::from(format!("/tmp/{}", name)),
+            100,
+            None,
+            Utc::now(),
+            provider.map(String::from),
+        );
+        record.completed_at = Utc::now() - Duration::days(days_ago);
+        record
+    }
+
+    #[tokio::test]
+    async fn test_download_history_enforce_retention() {
+        let temp_dir = tempdir().unwrap();
+        let mut history = DownloadHistory::open(temp_dir.path()).await.unwrap();
+        for (i, days_ago) in [40, 20, 10, 5, 1].iter().enumerate() {
+            history
+                .add(record_at(&format!("f{}.zip", i), None, *days_ago))
+                .await
+                .unwrap();
+        }
+
+        let removed = history
+            .enforce_retention(
+                HistoryRetention {
+                    max_age_days: 30,
+                    max_records: 3,
+                },
+                Utc::now(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(removed, 2);
+        // Newest records are kept
+        let names: Vec<_> = history.list().iter().map(|r| r.filename.clone()).collect();
+        assert_eq!(names, vec!["f4.zip", "f3.zip", "f2.zip"]);
+
+        let reopened = DownloadHistory::open(temp_dir.path()).await.unwrap();
+        assert_eq!(reopened.len(), 3);
+        let unchanged = history
+            .enforce_retention(HistoryRetention::default(), Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(unchanged, 0);
+    }
+
+    #[test]
+    fn test_history_filter_matches() {
+        let mut failed = record_at("a.zip", Some("github:owner/repo"), 3);
+        failed.status = DownloadStatus::Failed;
+        let recent = record_at("b.zip", Some("npm"), 0);
+
+        let filter = HistoryFilter {
+            status: vec![DownloadStatus::Failed],
+            provider: Some("GitHub".into()),
+            ..Default::default()
+        };
+        assert!(filter.matches(&failed));
+        assert!(!filter.matches(&recent));
+
+        let range = HistoryFilter {
+            from: Some(Utc::now() - Duration::days(1)),
+            ..Default::default()
+        };
+        assert!(!range.matches(&failed));
+        assert!(range.matches(&recent));
+        assert!(HistoryFilter::default().matches(&failed));
+    }
+
+    #[test]
+    fn test_csv_field_escaping_roundtrip() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+
+        let rows = parse_csv_rows("x,\"a,b\",\"say \"\"hi\"\"\"\r\n\"multi\nline\",,z\n");
+        assert_eq!(
+            rows,
+            vec![
+                vec!["x".to_string(), "a,b".into(), "say \"hi\"".into()],
+                vec!["multi\nline".to_string(), "".into(), "z".into()],
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_download_history_export_import_roundtrip() {
+        let temp_dir = tempdir().unwrap();
+        let mut history = DownloadHistory::open(temp_dir.path()).await.unwrap();
+        let mut tricky = record_at("report, \"final\".zip", Some("github"), 2);
+        tricky.url = "https://example.com/dl?a=1,b=\"2\"".into();
+        tricky.error = Some("line1\nline2".into());
+        tricky.status = DownloadStatus::Failed;
+        history.add(tricky.clone()).await.unwrap();
+        history
+            .add(record_at("other.zip", Some("npm"), 1))
+            .await
+            .unwrap();
+
+        let csv_path = temp_dir.path().join("export").join("history.csv");
+        let count = history
+            .export(
+                HistoryExportFormat::Csv,
+                &csv_path,
+                &HistoryFilter::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+
+        let json_path = temp_dir.path().join("history.json");
+        let filter = HistoryFilter {
+            provider: Some("npm".into()),
+            ..Default::default()
+        };
+        let count = history
+            .export(HistoryExportFormat::Json, &json_path, &filter)
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+
+        let target_dir = tempdir().unwrap();
+        let mut restored = DownloadHistory::open(target_dir.path()).await.unwrap();
+        let result = restored
+            .import(&csv_path, None, HistoryRetention::default(), Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(result.imported, 2);
+        let imported = restored.get(&tricky.id).unwrap();
+        assert_eq!(imported.filename, tricky.filename);
+        assert_eq!(imported.url, tricky.url);
+        assert_eq!(imported.error, tricky.error);
+        assert_eq!(imported.status, DownloadStatus::Failed);
+        assert_eq!(imported.completed_at, tricky.completed_at);
+
+        // Re-importing skips records that already exist
+        let again = restored
+            .import(&json_path, None, HistoryRetention::default(), Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(again.imported, 0);
+        assert_eq!(again.skipped, 1);
+
+        let empty_path = temp_dir.path().join("empty.json");
+        let count = history
+            .export(
+                HistoryExportFormat::Json,
+                &empty_path,
+                &HistoryFilter {
+                    provider: Some("none".into()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(count, 0);
+        let empty = restored
+            .import(&empty_path, None, HistoryRetention::default(), Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(empty.total, 0);
+    }
+
+    #[tokio::test]
+    async fn test_download_history_import_applies_retention() {
+        let temp_dir = tempdir().unwrap();
+        let mut history = DownloadHistory::open(temp_dir.path()).await.unwrap();
+        for (i, days_ago) in [50, 3, 2, 1].iter().enumerate() {
+            history
+                .add(record_at(&format!("f{}.zip", i), None, *days_ago))
+                .await
+                .unwrap();
+        }
+        let path = temp_dir.path().join("history.json");
+        history
+            .export(HistoryExportFormat::Json, &path, &HistoryFilter::default())
+            .await
+            .unwrap();
+
+        let target_dir = tempdir().unwrap();
+        let mut restored = DownloadHistory::open(target_dir.path()).await.unwrap();
+        let retention = HistoryRetention {
+            max_age_days: 30,
+            max_records: 2,
+        };
+        let result = restored
+            .import(&path, None, retention, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(result.imported, 2);
+        assert_eq!(result.skipped, 2);
+        let names: Vec<_> = restored.list().iter().map(|r| r.filename.clone()).collect();
+        assert_eq!(names, vec!["f3.zip", "f2.zip"]);
+    }
 }
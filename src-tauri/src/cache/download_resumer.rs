@@ -1,9 +1,25 @@
 use crate::error::{CogniaError, CogniaResult};
 use crate::platform::fs;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+/// Bytes before a checkpoint covered by its tail checksum
+pub const JOURNAL_TAIL_BYTES: u64 = 64 * 1024;
+/// Checkpoints kept per download; older ones are dropped
+const MAX_JOURNAL_CHECKPOINTS: usize = 8;
+
+/// A durable position in a partial file: every byte before `offset` was
+/// fsynced before this checkpoint was recorded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalCheckpoint {
+    pub offset: u64,
+    /// SHA-256 of the `JOURNAL_TAIL_BYTES` preceding `offset`
+    pub tail_sha256: String,
+}
 
 /// Download resumption support
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +32,176 @@ pub struct PartialDownload {
     pub started_at: i64,
     pub last_updated: i64,
     pub supports_resume: bool,
+    /// Verified checkpoints, oldest first. Empty for records written before
+    /// journaling, which fall back to trusting the on-disk size.
+    #[serde(default)]
+    pub journal: Vec<JournalCheckpoint>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PartialStatus {
+    /// On-disk bytes match the newest checkpoint
+    Consistent,
+    /// The partial file is gone
+    Missing,
+    /// File is shorter than the newest checkpoint
+    Truncated,
+    /// File has unverified bytes past the newest checkpoint
+    OverLength,
+    /// Tail checksum of the newest checkpoint no longer matches
+    TailMismatch,
+    /// Legacy record without a journal; cannot be verified
+    Unjournaled,
+}
+
+/// Outcome of checking a partial file against its journal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartialValidation {
+    pub url: String,
+    pub file_path: String,
+    pub status: PartialStatus,
+    pub journal_size: u64,
+    pub disk_size: u64,
+    /// Largest offset whose tail checksum still matches
+    pub verified_size: u64,
+    /// Whether the file was truncated back to `verified_size`
+    pub repaired: bool,
+}
+
+impl PartialDownload {
+    pub fn new(url: &str, file_path: PathBuf) -> Self {
+        let now = chrono::Utc::now().timestamp();
+        Self {
+            url: url.to_string(),
+            file_path,
+            expected_size: None,
+            downloaded_size: 0,
+            expected_checksum: None,
+            started_at: now,
+            last_updated: now,
+            supports_resume: false,
+            journal: Vec::new(),
+        }
+    }
+
+    /// Size recorded by the newest checkpoint
+    pub fn journal_size(&self) -> u64 {
+        self.journal.last().map(|c| c.offset).unwrap_or(0)
+    }
+
+    /// Fsync `file` and record `offset` as durable. Progress is only
+    /// persisted through checkpoints, so a crash can never leave a record
+    /// ahead of the bytes on disk.
+    pub async fn checkpoint(&mut self, file: &tokio::fs::File, offset: u64) -> CogniaResult<()> {
+        file.sync_data().await?;
+        let tail_sha256 = tail_checksum(&self.file_path, offset).await?;
+        self.journal.retain(|c| c.offset < offset);
+        self.journal.push(JournalCheckpoint {
+            offset,
+            tail_sha256,
+        });
+        if self.journal.len() > MAX_JOURNAL_CHECKPOINTS {
+            let excess = self.journal.len() - MAX_JOURNAL_CHECKPOINTS;
+            self.journal.drain(..excess);
+        }
+        self.downloaded_size = offset;
+        self.last_updated = chrono::Utc::now().timestamp();
+        Ok(())
+    }
+
+    /// Check the partial file against the journal. With `repair`, truncate
+    /// it to the last verified checkpoint and drop newer checkpoints.
+    pub async fn verify(&mut self, repair: bool) -> CogniaResult<PartialValidation> {
+        let journal_size = self.journal_size();
+        let mut report = PartialValidation {
+            url: self.url.clone(),
+            file_path: self.file_path.display().to_string(),
+            status: PartialStatus::Consistent,
+            journal_size,
+            disk_size: 0,
+            verified_size: 0,
+            repaired: false,
+        };
+
+        let Ok(disk_size) = fs::file_size(&self.file_path).await else {
+            report.status = PartialStatus::Missing;
+            if repair {
+                self.journal.clear();
+                self.downloaded_size = 0;
+            }
+            return Ok(report);
+        };
+        report.disk_size = disk_size;
+
+        if self.journal.is_empty() {
+            report.status = PartialStatus::Unjournaled;
+            report.verified_size = disk_size;
+            return Ok(report);
+        }
+
+        let mut verified = None;
+        for (idx, checkpoint) in self.journal.iter().enumerate().rev() {
+            if checkpoint.offset > disk_size {
+                continue;
+            }
+            if tail_checksum(&self.file_path, checkpoint.offset).await? == checkpoint.tail_sha256 {
+                verified = Some(idx);
+                break;
+            }
+        }
+        let verified_size = verified.map(|idx| self.journal[idx].offset).unwrap_or(0);
+        report.verified_size = verified_size;
+        report.status = if disk_size < journal_size {
+            PartialStatus::Truncated
+        } else if verified_size < journal_size {
+            PartialStatus::TailMismatch
+        } else if disk_size > journal_size {
+            PartialStatus::OverLength
+        } else {
+            PartialStatus::Consistent
+        };
+
+        if repair && report.status != PartialStatus::Consistent {
+            let file = tokio::fs::OpenOptions::new()
+                .write(true)
+                .open(&self.file_path)
+                .await?;
+            file.set_len(verified_size).await?;
+            file.sync_all().await?;
+            self.journal
+                .truncate(verified.map(|idx| idx + 1).unwrap_or(0));
+            self.downloaded_size = verified_size;
+            self.last_updated = chrono::Utc::now().timestamp();
+            report.repaired = true;
+        }
+
+        Ok(report)
+    }
+
+    /// Load a standalone journal record written by `save_to`
+    pub async fn load_from(path: &Path) -> Option<Self> {
+        let content = fs::read_file_string(path).await.ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Atomically persist this record as a standalone journal file
+    pub async fn save_to(&self, path: &Path) -> CogniaResult<()> {
+        let content = serde_json::to_vec(self).map_err(|e| CogniaError::Internal(e.to_string()))?;
+        fs::write_file_atomic(path, &content).await?;
+        Ok(())
+    }
+}
+
+/// SHA-256 of the `JOURNAL_TAIL_BYTES` preceding `offset` in `path`
+pub async fn tail_checksum(path: &Path, offset: u64) -> CogniaResult<String> {
+    let start = offset.saturating_sub(JOURNAL_TAIL_BYTES);
+    let mut file = tokio::fs::File::open(path).await?;
+    file.seek(std::io::SeekFrom::Start(start)).await?;
+    let mut buf = vec![0u8; (offset - start) as usize];
+    file.read_exact(&mut buf).await?;
+    Ok(hex::encode(Sha256::digest(&buf)))
 }
 
 pub struct DownloadResumer {
@@ -61,9 +247,21 @@ pub async fn get_or_create(&mut self, url: &str) -> CogniaResult<PartialDownload
         if let Some(partial) = self.partials.get(&key) {
             // Check if partial file still exists
             if fs::exists(&partial.file_path).await {
-                let size = fs::file_size(&partial.file_path).await?;
                 let mut updated = partial.clone();
-                updated.downloaded_size = size;
+                if updated.journal.is_empty() {
+                    updated.downloaded_size = fs::file_size(&partial.file_path).await?;
+                } else {
+                    // Never resume past the last fsynced checkpoint
+                    let report = updated.verify(true).await?;
+                    if report.repaired {
+                        log::warn!(
+                            "Partial download {} was {:?}; truncated to {} bytes",
+                            url,
+                            report.status,
+                            report.verified_size
+                        );
+                    }
+                }
                 updated.last_updated = chrono::Utc::now().timestamp();
                 self.partials.insert(key.clone(), updated.clone());
                 self.save().await?;
@@ -73,18 +271,7 @@ pub async fn get_or_create(&mut self, url: &str) -> CogniaResult<PartialDownload
 
         // Create new partial download
         let file_path = self.cache_dir.join(format!("{}.partial", key));
-        let now = chrono::Utc::now().timestamp();
-
-        let partial = PartialDownload {
-            url: url.to_string(),
-            file_path,
-            expected_size: None,
-            downloaded_size: 0,
-            expected_checksum: None,
-            started_at: now,
-            last_updated: now,
-            supports_resume: false,
-        };
+        let partial = PartialDownload::new(url, file_path);
 
         self.partials.insert(key, partial.clone());
         self.save().await?;
@@ -103,6 +290,36 @@ pub async fn update(&mut self, url: &str, downloaded_size: u64) -> CogniaResult<
         Ok(())
     }
 
+    /// Fsync `file` and persist `offset` as the verified progress of `url`
+    pub async fn checkpoint(
+        &mut self,
+        url: &str,
+        file: &tokio::fs::File,
+        offset: u64,
+    ) -> CogniaResult<()> {
+        let key = Self::url_key(url);
+        let Some(partial) = self.partials.get_mut(&key) else {
+            return Err(CogniaError::Internal(format!(
+                "No partial download recorded for {}",
+                url
+            )));
+        };
+        partial.checkpoint(file, offset).await?;
+        self.save().await
+    }
+
+    /// Check every tracked partial against its journal, optionally repairing
+    pub async fn validate_all(&mut self, repair: bool) -> CogniaResult<Vec<PartialValidation>> {
+        let mut reports = Vec::with_capacity(self.partials.len());
+        for partial in self.partials.values_mut() {
+            reports.push(partial.verify(repair).await?);
+        }
+        if repair {
+            self.save().await?;
+        }
+        Ok(reports)
+    }
+
     /// Mark download as complete
     pub async fn complete(&mut self, url: &str) -> CogniaResult<()> {
         let key = Self::url_key(url);
@@ -171,7 +388,7 @@ pub async fn clean_stale_with_option(
         Ok(count)
     }
 
-    fn url_key(url: &str) -> String {
+    pub(crate) fn url_key(url: &str) -> String {
         use std::collections::hash_map::DefaultHasher;
         use std::hash::{Hash, Hasher};
 
@@ -430,4 +647,166 @@ async fn test_cancel_nonexistent_url() {
             .unwrap();
         assert!(resumer.partials.is_empty());
     }
+
+    /// Write `len` deterministic bytes and checkpoint every `step` bytes
+    async fn journaled_partial(dir: &Path, len: usize, step: usize) -> PartialDownload {
+        let path = dir.join("file.partial");
+        let data: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+        tokio::fs::write(&path, &data).await.unwrap();
+
+        let file = tokio::fs::OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .await
+            .unwrap();
+        let mut partial = PartialDownload::new("https://example.com/big.zip", path);
+        for offset in (step..=len).step_by(step) {
+            partial.checkpoint(&file, offset as u64).await.unwrap();
+        }
+        partial
+    }
+
+    #[tokio::test]
+    async fn test_verify_consistent() {
+        let dir = tempdir().unwrap();
+        let mut partial = journaled_partial(dir.path(), 300_000, 100_000).await;
+        assert_eq!(partial.journal_size(), 300_000);
+        assert_eq!(partial.downloaded_size, 300_000);
+
+        let report = partial.verify(true).await.unwrap();
+        assert_eq!(report.status, PartialStatus::Consistent);
+        assert_eq!(report.verified_size, 300_000);
+        assert!(!report.repaired);
+    }
+
+    #[tokio::test]
+    async fn test_verify_truncated_file_rolls_back_to_checkpoint() {
+        let dir = tempdir().unwrap();
+        let mut partial = journaled_partial(dir.path(), 300_000, 100_000).await;
+
+        // Crash lost the tail of the file after the journal was written
+        let file = tokio::fs::OpenOptions::new()
+            .write(true)
+            .open(&partial.file_path)
+            .await
+            .unwrap();
+        file.set_len(250_000).await.unwrap();
+
+        let report = partial.verify(true).await.unwrap();
+        assert_eq!(report.status, PartialStatus::Truncated);
+        assert_eq!(report.disk_size, 250_000);
+        assert_eq!(report.verified_size, 200_000);
+        assert!(report.repaired);
+        assert_eq!(fs::file_size(&partial.file_path).await.unwrap(), 200_000);
+        assert_eq!(partial.journal_size(), 200_000);
+        assert_eq!(partial.downloaded_size, 200_000);
+    }
+
+    #[tokio::test]
+    async fn test_verify_over_length_file_drops_unverified_bytes() {
+        let dir = tempdir().unwrap();
+        let mut partial = journaled_partial(dir.path(), 200_000, 100_000).await;
+
+        // Bytes written after the last checkpoint were never journaled
+        let mut content = tokio::fs::read(&partial.file_path).await.unwrap();
+        content.extend_from_slice(&[0xAB; 5_000]);
+        tokio::fs::write(&partial.file_path, &content)
+            .await
+            .unwrap();
+
+        let report = partial.verify(false).await.unwrap();
+        assert_eq!(report.status, PartialStatus::OverLength);
+        assert!(!report.repaired);
+        assert_eq!(fs::file_size(&partial.file_path).await.unwrap(), 205_000);
+
+        let report = partial.verify(true).await.unwrap();
+        assert!(report.repaired);
+        assert_eq!(fs::file_size(&partial.file_path).await.unwrap(), 200_000);
+        assert_eq!(
+            partial.verify(false).await.unwrap().status,
+            PartialStatus::Consistent
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_tail_mismatch() {
+        let dir = tempdir().unwrap();
+        let mut partial = journaled_partial(dir.path(), 300_000, 100_000).await;
+
+        // Corrupt bytes just before the newest checkpoint
+        let mut content = tokio::fs::read(&partial.file_path).await.unwrap();
+        content[299_000] ^= 0xFF;
+        tokio::fs::write(&partial.file_path, &content)
+            .await
+            .unwrap();
+
+        let report = partial.verify(true).await.unwrap();
+        assert_eq!(report.status, PartialStatus::TailMismatch);
+        assert_eq!(report.verified_size, 200_000);
+        assert_eq!(fs::file_size(&partial.file_path).await.unwrap(), 200_000);
+    }
+
+    #[tokio::test]
+    async fn test_verify_missing_and_unjournaled() {
+        let dir = tempdir().unwrap();
+        let mut partial = journaled_partial(dir.path(), 100_000, 100_000).await;
+        tokio::fs::remove_file(&partial.file_path).await.unwrap();
+        let report = partial.verify(true).await.unwrap();
+        assert_eq!(report.status, PartialStatus::Missing);
+        assert!(partial.journal.is_empty());
+
+        let path = dir.path().join("legacy.partial");
+        fs::write_file_string(&path, "legacy").await.unwrap();
+        let mut legacy = PartialDownload::new("https://example.com/legacy.zip", path);
+        let report = legacy.verify(true).await.unwrap();
+        assert_eq!(report.status, PartialStatus::Unjournaled);
+        assert_eq!(report.verified_size, 6);
+    }
+
+    #[tokio::test]
+    async fn test_get_or_create_repairs_journaled_partial() {
+        let dir = tempdir().unwrap();
+        let cache_dir = dir.path().join("downloads");
+        let url = "https://example.com/journaled.zip";
+
+        let mut resumer = DownloadResumer::new(&cache_dir).await.unwrap();
+        let partial = resumer.get_or_create(url).await.unwrap();
+        let data = vec![7u8; 150_000];
+        tokio::fs::write(&partial.file_path, &data[..100_000])
+            .await
+            .unwrap();
+        let file = tokio::fs::OpenOptions::new()
+            .write(true)
+            .open(&partial.file_path)
+            .await
+            .unwrap();
+        resumer.checkpoint(url, &file, 100_000).await.unwrap();
+        drop(file);
+
+        // Simulate a crash after more bytes hit the disk but before a checkpoint
+        tokio::fs::write(&partial.file_path, &data).await.unwrap();
+
+        let mut reloaded = DownloadResumer::new(&cache_dir).await.unwrap();
+        let resumed = reloaded.get_or_create(url).await.unwrap();
+        assert_eq!(resumed.downloaded_size, 100_000);
+        assert_eq!(fs::file_size(&resumed.file_path).await.unwrap(), 100_000);
+
+        let reports = reloaded.validate_all(false).await.unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].status, PartialStatus::Consistent);
+    }
+
+    #[tokio::test]
+    async fn test_journal_save_and_load() {
+        let dir = tempdir().unwrap();
+        let partial = journaled_partial(dir.path(), 100_000, 50_000).await;
+        let journal_path = dir.path().join("task.journal.json");
+        partial.save_to(&journal_path).await.unwrap();
+
+        let loaded = PartialDownload::load_from(&journal_path).await.unwrap();
+        assert_eq!(loaded.journal, partial.journal);
+        assert!(PartialDownload::load_from(&dir.path().join("none.json"))
+            .await
+            .is_none());
+    }
 }
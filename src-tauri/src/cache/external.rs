@@ -65,6 +65,7 @@ pub enum ExternalCacheProvider {
     Cypress,
     Electron,
     Vcpkg,
+    Xmake,
     Sbt,
     // Terminal framework caches
     OhMyPosh,
@@ -123,6 +124,7 @@ pub fn all() -> Vec<Self> {
             Self::Cypress,
             Self::Electron,
             Self::Vcpkg,
+            Self::Xmake,
             Self::Sbt,
             // Terminal framework caches
             Self::OhMyPosh,
@@ -180,6 +182,7 @@ pub fn id(&self) -> &'static str {
             Self::Cypress => "cypress",
             Self::Electron => "electron",
             Self::Vcpkg => "vcpkg",
+            Self::Xmake => "xmake",
             Self::Sbt => "sbt",
             // Terminal framework caches
             Self::OhMyPosh => "oh_my_posh",
@@ -237,6 +240,7 @@ pub fn display_name(&self) -> &'static str {
             Self::Cypress => "Cypress",
             Self::Electron => "Electron",
             Self::Vcpkg => "vcpkg (C++)",
+            Self::Xmake => "xmake (C/C++)",
             Self::Sbt => "sbt/Ivy (Scala)",
             // Terminal framework caches
             Self::OhMyPosh => "Oh My Posh",
@@ -294,6 +298,7 @@ pub fn command(&self) -> &'static str {
             Self::Cypress => "cypress",
             Self::Electron => "electron",
             Self::Vcpkg => "vcpkg",
+            Self::Xmake => "xmake",
             Self::Sbt => "sbt",
             // Terminal framework caches
             Self::OhMyPosh => "oh-my-posh",
@@ -351,6 +356,7 @@ pub fn cache_path(&self) -> Option<PathBuf> {
             Self::Cypress => get_cypress_cache_path(),
             Self::Electron => get_electron_cache_path(),
             Self::Vcpkg => get_vcpkg_cache_path(),
+            Self::Xmake => get_xmake_cache_path(),
             Self::Sbt => get_sbt_cache_path(),
             // Terminal framework caches
             Self::OhMyPosh => get_oh_my_posh_ext_cache_path(),
@@ -408,6 +414,7 @@ pub fn clean_command(&self) -> Option<(&'static str, &'static [&'static str])> {
             Self::Cypress => None,  // Direct delete
             Self::Electron => None, // Direct delete
             Self::Vcpkg => None,    // Direct delete of binary cache
+            Self::Xmake => Some(("xrepo", &["clean", "-y", "--cache"])),
             Self::Sbt => None,      // Direct delete of ivy cache
             // Terminal framework caches - all direct delete (clean contents)
             Self::OhMyPosh => None,
@@ -465,6 +472,7 @@ pub fn parse_str(s: &str) -> Option<Self> {
             "cypress" => Some(Self::Cypress),
             "electron" => Some(Self::Electron),
             "vcpkg" => Some(Self::Vcpkg),
+            "xmake" | "xrepo" => Some(Self::Xmake),
             "sbt" | "ivy" | "ivy2" => Some(Self::Sbt),
             // Terminal framework caches
             "oh_my_posh" | "oh-my-posh" | "ohmyposh" => Some(Self::OhMyPosh),
@@ -493,6 +501,7 @@ pub fn category(&self) -> &'static str {
             | Self::Cypress
             | Self::Electron
             | Self::Vcpkg
+            | Self::Xmake
             | Self::Sbt => "devtools",
             #[cfg(target_os = "macos")]
             Self::CocoaPods => "devtools",
@@ -1696,6 +1705,33 @@ fn get_vcpkg_cache_path() -> Option<PathBuf> {
     }
 }
 
+/// xmake's global data directory (`~/.xmake`, or `$XMAKE_GLOBALDIR/.xmake`)
+pub(crate) fn get_xmake_global_dir() -> Option<PathBuf> {
+    if let Ok(dir) = std::env::var("XMAKE_GLOBALDIR") {
+        return Some(PathBuf::from(dir).join(".xmake"));
+    }
+
+    #[cfg(windows)]
+    {
+        std::env::var("USERPROFILE")
+            .ok()
+            .map(|p| PathBuf::from(p).join(".xmake"))
+    }
+    #[cfg(not(windows))]
+    {
+        dirs::home_dir().map(|h| h.join(".xmake"))
+    }
+}
+
+/// Downloaded package sources and archives
+pub(crate) fn get_xmake_cache_path() -> Option<PathBuf> {
+    // Check XMAKE_PKG_CACHEDIR first
+    if let Ok(cache) = std::env::var("XMAKE_PKG_CACHEDIR") {
+        return Some(PathBuf::from(cache));
+    }
+    get_xmake_global_dir().map(|dir| dir.join("cache").join("packages"))
+}
+
 fn get_sbt_cache_path() -> Option<PathBuf> {
     // Check SBT_IVY_HOME first (official system property, also works as env var)
     if let Ok(ivy_home) = std::env::var("SBT_IVY_HOME") {
@@ -3060,6 +3096,7 @@ fn test_devtools_provider_ids() {
         assert_eq!(ExternalCacheProvider::Cypress.id(), "cypress");
         assert_eq!(ExternalCacheProvider::Electron.id(), "electron");
         assert_eq!(ExternalCacheProvider::Vcpkg.id(), "vcpkg");
+        assert_eq!(ExternalCacheProvider::Xmake.id(), "xmake");
         assert_eq!(ExternalCacheProvider::Sbt.id(), "sbt");
     }
 
@@ -3089,6 +3126,10 @@ fn test_devtools_provider_from_str() {
             ExternalCacheProvider::parse_str("vcpkg"),
             Some(ExternalCacheProvider::Vcpkg)
         );
+        assert_eq!(
+            ExternalCacheProvider::parse_str("xrepo"),
+            Some(ExternalCacheProvider::Xmake)
+        );
         assert_eq!(
             ExternalCacheProvider::parse_str("sbt"),
             Some(ExternalCacheProvider::Sbt)
@@ -3256,6 +3297,7 @@ fn test_devtools_cache_paths_not_panic() {
         let _ = get_cypress_cache_path();
         let _ = get_electron_cache_path();
         let _ = get_vcpkg_cache_path();
+        let _ = get_xmake_cache_path();
         let _ = get_sbt_cache_path();
     }
 
@@ -0,0 +1,552 @@
+//! Preview and guardrails for force-cleaning external caches.
+//!
+//! A force clean must be preceded by a preview of the same provider: the
+//! preview reports what would be removed and how costly it is to re-create,
+//! and issues a single-use token that the clean has to present within
+//! [`PREVIEW_TOKEN_TTL`]. Providers whose tools support it also expose
+//! partial modes that keep the expensive part of the cache.
+
+use super::external::{calculate_dir_size, ExternalCacheProvider};
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::{disk::format_size, fs};
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long a preview token stays valid
+pub const PREVIEW_TOKEN_TTL: Duration = Duration::from_secs(10 * 60);
+/// Mode id of a complete clean
+pub const FULL_CLEAN_MODE: &str = "full";
+/// Largest entries listed individually in a preview
+const MAX_PREVIEW_PATHS: usize = 20;
+
+/// How costly it is for the owning tool to re-create cleaned data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegenerationCost {
+    /// Re-downloaded quickly on demand
+    Cheap,
+    /// Large re-downloads, index rebuilds or recompilation
+    Expensive,
+}
+
+/// What a partial clean does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartialCleanAction {
+    /// Run the tool's own partial clean command
+    Command(&'static str, &'static [&'static str]),
+    /// Remove only these subdirectories of the cache path
+    Subpaths(&'static [&'static str]),
+    /// Keep only the newest downloaded archive of each crate
+    CargoKeepLatest,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PartialCleanMode {
+    pub id: &'static str,
+    pub description: &'static str,
+    pub action: PartialCleanAction,
+}
+
+/// Partial clean modes supported by a provider.
+pub fn partial_modes(provider: ExternalCacheProvider) -> &'static [PartialCleanMode] {
+    match provider {
+        ExternalCacheProvider::Npm => &[PartialCleanMode {
+            id: "verify",
+            description: "Garbage-collect unreferenced data and verify the cache index",
+            action: PartialCleanAction::Command("npm", &["cache", "verify"]),
+        }],
+        ExternalCacheProvider::Cargo => &[PartialCleanMode {
+            id: "keep_latest",
+            description: "Keep only the newest downloaded archive of each crate",
+            action: PartialCleanAction::CargoKeepLatest,
+        }],
+        ExternalCacheProvider::Pip => &[PartialCleanMode {
+            id: "http_only",
+            description: "Remove cached HTTP responses but keep locally built wheels",
+            action: PartialCleanAction::Subpaths(&["http", "http-v2"]),
+        }],
+        ExternalCacheProvider::Uv => &[PartialCleanMode {
+            id: "prune",
+            description: "Remove cache entries no longer used by any project",
+            action: PartialCleanAction::Command("uv", &["cache", "prune"]),
+        }],
+        ExternalCacheProvider::Conda => &[
+            PartialCleanMode {
+                id: "tarballs",
+                description: "Remove package tarballs but keep extracted packages",
+                action: PartialCleanAction::Command("conda", &["clean", "--tarballs", "-y"]),
+            },
+            PartialCleanMode {
+                id: "index_cache",
+                description: "Remove only the cached channel index",
+                action: PartialCleanAction::Command("conda", &["clean", "--index-cache", "-y"]),
+            },
+        ],
+        #[cfg(not(windows))]
+        ExternalCacheProvider::Brew => &[PartialCleanMode {
+            id: "keep_latest",
+            description: "Remove downloads of outdated versions only",
+            action: PartialCleanAction::Command("brew", &["cleanup"]),
+        }],
+        ExternalCacheProvider::Dotnet => &[PartialCleanMode {
+            id: "http_cache",
+            description: "Clear the NuGet HTTP cache but keep restored packages",
+            action: PartialCleanAction::Command(
+                "dotnet",
+                &["nuget", "locals", "http-cache", "--clear"],
+            ),
+        }],
+        ExternalCacheProvider::Gradle => &[PartialCleanMode {
+            id: "build_cache",
+            description: "Remove the local build cache but keep downloaded dependencies",
+            action: PartialCleanAction::Subpaths(&["build-cache-1"]),
+        }],
+        _ => &[],
+    }
+}
+
+/// Look up a partial mode by id.
+pub fn find_partial_mode(
+    provider: ExternalCacheProvider,
+    mode: &str,
+) -> Option<&'static PartialCleanMode> {
+    partial_modes(provider).iter().find(|m| m.id == mode)
+}
+
+/// Cost of a full clean and what the tool has to re-create afterwards.
+pub fn regeneration(provider: ExternalCacheProvider) -> (RegenerationCost, &'static str) {
+    use ExternalCacheProvider as P;
+    match provider {
+        P::Cargo => (
+            RegenerationCost::Expensive,
+            "Crate archives are downloaded again on the next build",
+        ),
+        P::Conda => (
+            RegenerationCost::Expensive,
+            "Packages are downloaded and extracted again for every new environment",
+        ),
+        P::Go => (
+            RegenerationCost::Expensive,
+            "The module cache is downloaded again by the next build",
+        ),
+        P::Maven | P::Gradle | P::Sbt => (
+            RegenerationCost::Expensive,
+            "Dependencies are downloaded again by the next build",
+        ),
+        P::Dotnet => (
+            RegenerationCost::Expensive,
+            "NuGet packages are restored again by the next build",
+        ),
+        P::Docker | P::Podman => (
+            RegenerationCost::Expensive,
+            "Build layers are rebuilt by the next image build",
+        ),
+        P::Vcpkg => (
+            RegenerationCost::Expensive,
+            "Binary packages are rebuilt from source",
+        ),
+        P::Flutter => (
+            RegenerationCost::Expensive,
+            "Pub packages are downloaded again by the next build",
+        ),
+        P::Npm
+        | P::Pnpm
+        | P::Yarn
+        | P::Pip
+        | P::Uv
+        | P::Bun
+        | P::Deno
+        | P::Composer
+        | P::Poetry
+        | P::Bundler
+        | P::Gem => (
+            RegenerationCost::Cheap,
+            "Packages are downloaded again on the next install",
+        ),
+        _ => (
+            RegenerationCost::Cheap,
+            "Re-created automatically the next time the tool needs it",
+        ),
+    }
+}
+
+/// Cost of one top-level entry of a provider's cache.
+pub fn path_cost(provider: ExternalCacheProvider, name: &str) -> RegenerationCost {
+    match (provider, name) {
+        // Built wheels may need a compiler to re-create
+        (ExternalCacheProvider::Pip, "wheels") => RegenerationCost::Expensive,
+        (ExternalCacheProvider::Pip, "http" | "http-v2") => RegenerationCost::Cheap,
+        _ => regeneration(provider).0,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalCleanPathItem {
+    pub path: String,
+    pub size: u64,
+    pub size_human: String,
+    pub cost: RegenerationCost,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalCleanModeInfo {
+    pub id: String,
+    pub description: String,
+    /// Whether the mode runs the tool's own command
+    pub uses_command: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalCleanPreview {
+    pub provider: String,
+    pub display_name: String,
+    pub cache_path: Option<String>,
+    pub total_size: u64,
+    pub total_size_human: String,
+    /// Largest top-level entries of the cache, biggest first
+    pub paths: Vec<ExternalCleanPathItem>,
+    pub cost: RegenerationCost,
+    /// What the tool must re-create after a full clean
+    pub regenerates: String,
+    pub modes: Vec<ExternalCleanModeInfo>,
+    /// Token to pass to `cache_force_clean_external`
+    pub token: String,
+    pub token_expires_at: DateTime<Utc>,
+}
+
+/// Build the preview for `provider` and issue its clean token.
+pub async fn build_preview(provider: ExternalCacheProvider) -> ExternalCleanPreview {
+    let cache_path = provider.cache_path();
+    let mut entries = match cache_path {
+        Some(ref path) => list_top_level(path).await,
+        None => Vec::new(),
+    };
+    let total_size: u64 = entries.iter().map(|(_, size)| size).sum();
+    entries.sort_by(|a, b| b.1.cmp(&a.1));
+    entries.truncate(MAX_PREVIEW_PATHS);
+
+    let paths = entries
+        .into_iter()
+        .map(|(path, size)| {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            ExternalCleanPathItem {
+                path: path.display().to_string(),
+                size,
+                size_human: format_size(size),
+                cost: path_cost(provider, &name),
+            }
+        })
+        .collect();
+
+    let (cost, regenerates) = regeneration(provider);
+    let mut modes = vec![ExternalCleanModeInfo {
+        id: FULL_CLEAN_MODE.to_string(),
+        description: "Remove the entire cache".to_string(),
+        uses_command: provider.clean_command().is_some(),
+    }];
+    modes.extend(
+        partial_modes(provider)
+            .iter()
+            .map(|m| ExternalCleanModeInfo {
+                id: m.id.to_string(),
+                description: m.description.to_string(),
+                uses_command: matches!(m.action, PartialCleanAction::Command(..)),
+            }),
+    );
+
+    let token = issue_preview_token(provider.id());
+    ExternalCleanPreview {
+        provider: provider.id().to_string(),
+        display_name: provider.display_name().to_string(),
+        cache_path: cache_path.map(|p| p.display().to_string()),
+        total_size,
+        total_size_human: format_size(total_size),
+        paths,
+        cost,
+        regenerates: regenerates.to_string(),
+        modes,
+        token,
+        token_expires_at: Utc::now()
+            + chrono::Duration::from_std(PREVIEW_TOKEN_TTL).unwrap_or_default(),
+    }
+}
+
+async fn list_top_level(path: &Path) -> Vec<(PathBuf, u64)> {
+    let mut entries = Vec::new();
+    let Ok(mut dir) = tokio::fs::read_dir(path).await else {
+        return entries;
+    };
+    while let Ok(Some(entry)) = dir.next_entry().await {
+        let entry_path = entry.path();
+        let size = match entry.metadata().await {
+            Ok(meta) if meta.is_dir() => calculate_dir_size(&entry_path).await,
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        };
+        entries.push((entry_path, size));
+    }
+    entries
+}
+
+// ── Preview tokens ──────────────────────────────────────────────────────────
+
+struct IssuedToken {
+    provider: String,
+    issued_at: Instant,
+}
+
+static PREVIEW_TOKENS: Lazy<Mutex<HashMap<String, IssuedToken>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Issue a single-use clean token for `provider`.
+pub fn issue_preview_token(provider: &str) -> String {
+    issue_token_at(provider, Instant::now())
+}
+
+/// Validate and consume a token issued by a preview of `provider`.
+pub fn consume_preview_token(token: &str, provider: &str) -> CogniaResult<()> {
+    consume_token_at(token, provider, Instant::now())
+}
+
+fn issue_token_at(provider: &str, now: Instant) -> String {
+    let token = uuid::Uuid::new_v4().to_string();
+    let mut tokens = PREVIEW_TOKENS.lock().unwrap_or_else(|e| e.into_inner());
+    tokens.retain(|_, issued| now.duration_since(issued.issued_at) < PREVIEW_TOKEN_TTL);
+    tokens.insert(
+        token.clone(),
+        IssuedToken {
+            provider: provider.to_string(),
+            issued_at: now,
+        },
+    );
+    token
+}
+
+fn consume_token_at(token: &str, provider: &str, now: Instant) -> CogniaResult<()> {
+    let issued = PREVIEW_TOKENS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(token)
+        .ok_or_else(|| {
+            CogniaError::PermissionDenied(
+                "Unknown or already used preview token; preview the clean first".into(),
+            )
+        })?;
+    if issued.provider != provider {
+        return Err(CogniaError::PermissionDenied(format!(
+            "Preview token was issued for '{}', not '{}'",
+            issued.provider, provider
+        )));
+    }
+    if now.duration_since(issued.issued_at) >= PREVIEW_TOKEN_TTL {
+        return Err(CogniaError::PermissionDenied(format!(
+            "Preview token expired after {} minutes; preview the clean again",
+            PREVIEW_TOKEN_TTL.as_secs() / 60
+        )));
+    }
+    Ok(())
+}
+
+// ── Partial clean ───────────────────────────────────────────────────────────
+
+/// Remove the paths a filesystem-based partial mode targets under
+/// `cache_path`. Returns each removed path with its size.
+pub async fn clean_partial_paths(
+    action: PartialCleanAction,
+    cache_path: &Path,
+    use_trash: bool,
+) -> CogniaResult<Vec<(PathBuf, u64)>> {
+    let targets = match action {
+        PartialCleanAction::Command(..) => return Ok(Vec::new()),
+        PartialCleanAction::Subpaths(subpaths) => {
+            let mut targets = Vec::new();
+            for sub in subpaths {
+                let path = cache_path.join(sub);
+                if fs::exists(&path).await {
+                    let size = calculate_dir_size(&path).await;
+                    targets.push((path, size));
+                }
+            }
+            targets
+        }
+        PartialCleanAction::CargoKeepLatest => outdated_crate_archives(cache_path).await?,
+    };
+
+    for (path, _) in &targets {
+        if fs::is_dir(path).await {
+            fs::remove_dir_with_option(path, use_trash).await?;
+        } else {
+            fs::remove_file_with_option(path, use_trash).await?;
+        }
+    }
+    Ok(targets)
+}
+
+/// Split `serde-1.0.200` into the crate name and its version.
+fn split_crate_file(stem: &str) -> Option<(&str, semver::Version)> {
+    stem.match_indices('-').find_map(|(i, _)| {
+        semver::Version::parse(&stem[i + 1..])
+            .ok()
+            .map(|version| (&stem[..i], version))
+    })
+}
+
+/// Crate archives in `registry/cache/<index>/` that have a newer version
+/// of the same crate next to them.
+async fn outdated_crate_archives(cache_path: &Path) -> CogniaResult<Vec<(PathBuf, u64)>> {
+    let mut outdated = Vec::new();
+    let mut registries = tokio::fs::read_dir(cache_path).await?;
+    while let Some(registry) = registries.next_entry().await? {
+        if !registry.file_type().await?.is_dir() {
+            continue;
+        }
+        let mut by_crate: HashMap<String, Vec<(semver::Version, PathBuf, u64)>> = HashMap::new();
+        let mut archives = tokio::fs::read_dir(registry.path()).await?;
+        while let Some(archive) = archives.next_entry().await? {
+            let path = archive.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("crate") {
+                continue;
+            }
+            let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let Some((name, version)) = split_crate_file(stem) else {
+                continue;
+            };
+            let size = archive.metadata().await.map(|m| m.len()).unwrap_or(0);
+            by_crate
+                .entry(name.to_string())
+                .or_default()
+                .push((version, path, size));
+        }
+        for mut versions in by_crate.into_values() {
+            versions.sort_by(|a, b| b.0.cmp(&a.0));
+            outdated.extend(
+                versions
+                    .into_iter()
+                    .skip(1)
+                    .map(|(_, path, size)| (path, size)),
+            );
+        }
+    }
+    outdated.sort();
+    Ok(outdated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_split_crate_file() {
+        let (name, version) = split_crate_file("serde-1.0.200").unwrap();
+        assert_eq!(name, "serde");
+        assert_eq!(version, semver::Version::new(1, 0, 200));
+
+        let (name, version) = split_crate_file("tokio-util-0.7.10").unwrap();
+        assert_eq!(name, "tokio-util");
+        assert_eq!(version.minor, 7);
+
+        let (name, version) = split_crate_file("foo-2d-1.0.0-beta.1").unwrap();
+        assert_eq!(name, "foo-2d");
+        assert_eq!(version.pre.as_str(), "beta.1");
+
+        assert!(split_crate_file("not-a-crate").is_none());
+    }
+
+    #[test]
+    fn test_preview_token_is_single_use_and_provider_bound() {
+        let now = Instant::now();
+        let token = issue_token_at("npm", now);
+        assert!(consume_token_at(&token, "pip", now).is_err());
+        // A mismatched attempt still consumes the token
+        assert!(consume_token_at(&token, "npm", now).is_err());
+
+        let token = issue_token_at("npm", now);
+        assert!(consume_token_at(&token, "npm", now).is_ok());
+        assert!(consume_token_at(&token, "npm", now).is_err());
+    }
+
+    #[test]
+    fn test_preview_token_expires() {
+        let now = Instant::now();
+        let token = issue_token_at("cargo", now);
+        let later = now + PREVIEW_TOKEN_TTL + Duration::from_secs(1);
+        assert!(consume_token_at(&token, "cargo", later).is_err());
+    }
+
+    #[test]
+    fn test_partial_modes_and_costs() {
+        assert!(find_partial_mode(ExternalCacheProvider::Npm, "verify").is_some());
+        assert!(find_partial_mode(ExternalCacheProvider::Npm, "tarballs").is_none());
+        assert_eq!(
+            regeneration(ExternalCacheProvider::Cargo).0,
+            RegenerationCost::Expensive
+        );
+        assert_eq!(
+            path_cost(ExternalCacheProvider::Pip, "wheels"),
+            RegenerationCost::Expensive
+        );
+        assert_eq!(
+            path_cost(ExternalCacheProvider::Pip, "http-v2"),
+            RegenerationCost::Cheap
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cargo_keep_latest_removes_older_archives() {
+        let dir = tempdir().unwrap();
+        let registry = dir.path().join("index.crates.io-6f17d22bba15001f");
+        std::fs::create_dir_all(&registry).unwrap();
+        for file in [
+            "serde-1.0.190.crate",
+            "serde-1.0.200.crate",
+            "serde-1.0.9.crate",
+            "log-0.4.21.crate",
+        ] {
+            std::fs::write(registry.join(file), b"archive").unwrap();
+        }
+
+        let removed = clean_partial_paths(PartialCleanAction::CargoKeepLatest, dir.path(), false)
+            .await
+            .unwrap();
+        let removed: Vec<_> = removed
+            .iter()
+            .map(|(p, _)| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(removed, vec!["serde-1.0.190.crate", "serde-1.0.9.crate"]);
+        assert!(registry.join("serde-1.0.200.crate").exists());
+        assert!(registry.join("log-0.4.21.crate").exists());
+    }
+
+    #[tokio::test]
+    async fn test_subpaths_mode_keeps_other_entries() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("http-v2")).unwrap();
+        std::fs::create_dir_all(dir.path().join("wheels")).unwrap();
+        std::fs::write(dir.path().join("http-v2").join("a"), b"data").unwrap();
+
+        let removed = clean_partial_paths(
+            PartialCleanAction::Subpaths(&["http", "http-v2"]),
+            dir.path(),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(removed.len(), 1);
+        assert!(!dir.path().join("http-v2").exists());
+        assert!(dir.path().join("wheels").exists());
+    }
+}
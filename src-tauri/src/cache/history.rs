@@ -5,7 +5,7 @@
 
 use crate::error::{CogniaError, CogniaResult};
 use crate::platform::{disk::format_size, fs};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
@@ -33,6 +33,9 @@ pub struct CleanupRecord {
     pub files: Vec<CleanedFileInfo>,
     /// Whether the list of files was truncated
     pub files_truncated: bool,
+    /// Clean mode used, for cleans that offer more than one (e.g. "full", "verify")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
 }
 
 /// Information about a single cleaned file
@@ -53,6 +56,9 @@ pub struct CleanedFileInfo {
 struct HistoryIndex {
     version: u32,
     records: Vec<CleanupRecord>,
+    /// Last month (`YYYY-MM`) a monthly summary was produced for
+    #[serde(default)]
+    summary_month: Option<String>,
 }
 
 /// Manager for cleanup history persistence
@@ -70,12 +76,12 @@ pub async fn open(cache_dir: &Path) -> CogniaResult<Self> {
             let content = fs::read_file_string(&history_path).await?;
             serde_json::from_str(&content).unwrap_or_else(|_| HistoryIndex {
                 version: 1,
-                records: Vec::new(),
+                ..Default::default()
             })
         } else {
             HistoryIndex {
                 version: 1,
-                records: Vec::new(),
+                ..Default::default()
             }
         };
 
@@ -120,6 +126,44 @@ pub fn count(&self) -> usize {
         self.index.records.len()
     }
 
+    /// All records, most recent first
+    pub fn records(&self) -> &[CleanupRecord] {
+        &self.index.records
+    }
+
+    /// Summarize the previous calendar month once, the first time this is
+    /// called in a new month
+    pub async fn take_monthly_summary(
+        &mut self,
+        now: DateTime<Utc>,
+    ) -> CogniaResult<Option<MonthlyCleanupSummary>> {
+        let Some(previous) = now.date_naive().with_day(1).and_then(|d| d.pred_opt()) else {
+            return Ok(None);
+        };
+        let month = previous.format("%Y-%m").to_string();
+        if self.index.summary_month.as_deref() == Some(month.as_str()) {
+            return Ok(None);
+        }
+
+        let in_month: Vec<&CleanupRecord> = self
+            .index
+            .records
+            .iter()
+            .filter(|r| r.timestamp.format("%Y-%m").to_string() == month)
+            .collect();
+        let freed_bytes = in_month.iter().map(|r| r.freed_bytes).sum();
+        let summary = MonthlyCleanupSummary {
+            month: month.clone(),
+            cleanups: in_month.len(),
+            freed_bytes,
+            freed_human: format_size(freed_bytes),
+        };
+
+        self.index.summary_month = Some(month);
+        self.save().await?;
+        Ok(Some(summary))
+    }
+
     /// Get summary statistics across all records
     pub fn summary(&self) -> CleanupSummary {
         let total_freed: u64 = self.index.records.iter().map(|r| r.freed_bytes).sum();
@@ -165,12 +209,23 @@ pub struct CleanupSummary {
     pub permanent_cleanups: usize,
 }
 
+/// Space reclaimed by cleanups during one calendar month
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonthlyCleanupSummary {
+    /// Month covered, as `YYYY-MM`
+    pub month: String,
+    pub cleanups: usize,
+    pub freed_bytes: u64,
+    pub freed_human: String,
+}
+
 /// Builder for creating CleanupRecord
 pub struct CleanupRecordBuilder {
     clean_type: String,
     use_trash: bool,
     freed_bytes: u64,
     files: Vec<CleanedFileInfo>,
+    mode: Option<String>,
 }
 
 impl CleanupRecordBuilder {
@@ -180,9 +235,15 @@ pub fn new(clean_type: impl Into<String>, use_trash: bool) -> Self {
             use_trash,
             freed_bytes: 0,
             files: Vec::new(),
+            mode: None,
         }
     }
 
+    pub fn mode(&mut self, mode: impl Into<String>) -> &mut Self {
+        self.mode = Some(mode.into());
+        self
+    }
+
     pub fn add_file(
         &mut self,
         path: impl Into<String>,
@@ -211,6 +272,7 @@ pub fn build(self) -> CleanupRecord {
             file_count,
             files: self.files,
             files_truncated: false,
+            mode: self.mode,
         }
     }
 }
@@ -389,7 +451,7 @@ async fn test_cleanup_record_files_truncation() {
     fn test_cleanup_summary_empty() {
         let history_index = HistoryIndex {
             version: 1,
-            records: Vec::new(),
+            ..Default::default()
         };
         // Create a CleanupHistory-like summary manually
         let total_freed: u64 = history_index.records.iter().map(|r| r.freed_bytes).sum();
@@ -399,6 +461,35 @@ fn test_cleanup_summary_empty() {
         assert_eq!(total_files, 0);
     }
 
+    #[tokio::test]
+    async fn test_monthly_summary_covers_previous_month_once() {
+        let dir = tempdir().unwrap();
+        let mut history = CleanupHistory::open(dir.path()).await.unwrap();
+
+        for (timestamp, size) in [
+            ("2026-02-27T10:00:00Z", 1000),
+            ("2026-03-02T10:00:00Z", 2000),
+            ("2026-03-30T10:00:00Z", 500),
+            ("2026-04-01T08:00:00Z", 9000),
+        ] {
+            let mut builder = CleanupRecordBuilder::new("downloads", false);
+            builder.add_file("/cache/file.bin", size, "download");
+            let mut record = builder.build();
+            record.timestamp = timestamp.parse().unwrap();
+            history.add(record).await.unwrap();
+        }
+
+        let now: DateTime<Utc> = "2026-04-03T12:00:00Z".parse().unwrap();
+        let summary = history.take_monthly_summary(now).await.unwrap().unwrap();
+        assert_eq!(summary.month, "2026-03");
+        assert_eq!(summary.cleanups, 2);
+        assert_eq!(summary.freed_bytes, 2500);
+
+        assert!(history.take_monthly_summary(now).await.unwrap().is_none());
+        let reopened = CleanupHistory::open(dir.path()).await.unwrap();
+        assert_eq!(reopened.index.summary_month.as_deref(), Some("2026-03"));
+    }
+
     #[tokio::test]
     async fn test_cleanup_history_most_recent_first() {
         let dir = tempdir().unwrap();
@@ -3,11 +3,45 @@
 //! Supports two migration modes:
 //! - **Move**: Move cache files to new location, update config to point to new path
 //! - **MoveAndLink**: Move cache files to new location, create symlink at old location
+//!
+//! Migrations run as copy-verify-swap: every file of the source index is copied
+//! and verified before the caller switches the configured cache path, and the
+//! source is only removed afterwards by [`complete_migration`]. Completed entries
+//! are recorded in a journal at the destination so an interrupted migration can
+//! be resumed by running it again.
 
 use crate::error::{CogniaError, CogniaResult};
 use crate::platform::{disk, fs};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+
+/// Journal file written at the destination while a migration is in progress
+pub const MIGRATION_JOURNAL_FILE: &str = ".cognia-migration-journal";
+
+/// Minimum delay between two progress reports while copying
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Filesystem types reported for network mounts on Unix-like systems
+#[cfg(not(windows))]
+const NETWORK_FS_TYPES: &[&str] = &[
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smbfs",
+    "smb3",
+    "afpfs",
+    "webdav",
+    "davfs",
+    "ncpfs",
+    "9p",
+    "glusterfs",
+    "ceph",
+];
 
 /// Migration mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -19,8 +53,40 @@ pub enum MigrationMode {
     MoveAndLink,
 }
 
+impl MigrationMode {
+    fn as_str(self) -> &'static str {
+        match self {
+            MigrationMode::Move => "move",
+            MigrationMode::MoveAndLink => "move_and_link",
+        }
+    }
+}
+
+/// Options controlling how a migration is executed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationOptions {
+    /// Keep the source directory after a successful migration
+    #[serde(default)]
+    pub keep_source: bool,
+    /// Compare SHA-256 hashes in addition to sizes when verifying copied files
+    #[serde(default)]
+    pub verify_hashes: bool,
+    /// Stops the copy between entries when set; the journal keeps the progress
+    #[serde(skip)]
+    pub cancel: Option<Arc<AtomicBool>>,
+}
+
+impl MigrationOptions {
+    fn is_cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(|cancel| cancel.load(Ordering::SeqCst))
+    }
+}
+
 /// Migration progress stage
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum MigrationStage {
     Validating,
@@ -42,6 +108,11 @@ pub struct MigrationProgress {
     pub message: String,
     pub bytes_copied: u64,
     pub total_bytes: u64,
+    pub files_copied: usize,
+    /// Files reused from an interrupted run (counted in `files_copied` too)
+    pub files_skipped: usize,
+    pub total_files: usize,
+    pub current_file: Option<String>,
     pub percent: f32,
 }
 
@@ -56,7 +127,12 @@ pub struct MigrationResult {
     pub bytes_migrated: u64,
     pub bytes_migrated_human: String,
     pub files_count: usize,
+    /// Files already present from an interrupted run and not copied again
+    pub files_skipped: usize,
     pub symlink_created: bool,
+    pub source_kept: bool,
+    /// Whether a failed migration left a journal that a re-run can resume from
+    pub resumable: bool,
     pub error: Option<String>,
 }
 
@@ -75,6 +151,9 @@ pub struct MigrationValidation {
     pub destination_space_human: String,
     pub has_enough_space: bool,
     pub is_same_drive: bool,
+    pub is_network_drive: bool,
+    /// Files recorded by the journal of an interrupted migration to this destination
+    pub resumable_files: usize,
     pub errors: Vec<String>,
     pub warnings: Vec<String>,
 }
@@ -155,6 +234,98 @@ fn is_same_drive(a: &Path, b: &Path) -> bool {
     }
 }
 
+/// Check if a path lives on a network share or remote mount
+fn is_network_path(path: &Path) -> bool {
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStrExt;
+        use std::path::{Component, Prefix};
+        use windows_sys::Win32::Storage::FileSystem::GetDriveTypeW;
+
+        const DRIVE_REMOTE: u32 = 4;
+
+        let Some(Component::Prefix(prefix)) = path.components().next() else {
+            return false;
+        };
+        match prefix.kind() {
+            Prefix::UNC(..) | Prefix::VerbatimUNC(..) => true,
+            Prefix::Disk(letter) | Prefix::VerbatimDisk(letter) => {
+                let root: Vec<u16> = std::ffi::OsStr::new(&format!("{}:\\", letter as char))
+                    .encode_wide()
+                    .chain(std::iter::once(0))
+                    .collect();
+                unsafe { GetDriveTypeW(root.as_ptr()) == DRIVE_REMOTE }
+            }
+            _ => false,
+        }
+    }
+    #[cfg(not(windows))]
+    {
+        // The destination may not exist yet; inspect the mount of its closest ancestor
+        path.ancestors()
+            .find(|p| p.exists())
+            .and_then(mount_fs_type)
+            .is_some_and(|fs_type| is_network_fs_type(&fs_type))
+    }
+}
+
+#[cfg(not(windows))]
+fn is_network_fs_type(fs_type: &str) -> bool {
+    let fs_type = fs_type.to_ascii_lowercase();
+    // FUSE mounts report e.g. "fuse.sshfs" or "fuse.rclone"
+    let base = fs_type.strip_prefix("fuse.").unwrap_or(&fs_type);
+    NETWORK_FS_TYPES.contains(&base) || matches!(base, "sshfs" | "rclone" | "davfs2")
+}
+
+#[cfg(target_os = "linux")]
+fn mount_fs_type(path: &Path) -> Option<String> {
+    let path = std::fs::canonicalize(path).ok()?;
+    let mounts = std::fs::read_to_string("/proc/mounts").ok()?;
+    find_mount_fs_type(&mounts, &path)
+}
+
+#[cfg(target_os = "macos")]
+fn mount_fs_type(path: &Path) -> Option<String> {
+    let path = std::fs::canonicalize(path).ok()?;
+    let output = std::process::Command::new("mount").output().ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    // Lines look like "//user@server/share on /Volumes/share (smbfs, nodev, nosuid)"
+    text.lines()
+        .filter_map(|line| {
+            let (_, rest) = line.split_once(" on ")?;
+            let (mount_point, options) = rest.rsplit_once(" (")?;
+            let fs_type = options.split([',', ')']).next()?.trim();
+            Some((PathBuf::from(mount_point), fs_type.to_string()))
+        })
+        .filter(|(mount_point, _)| path.starts_with(mount_point))
+        .max_by_key(|(mount_point, _)| mount_point.as_os_str().len())
+        .map(|(_, fs_type)| fs_type)
+}
+
+#[cfg(all(unix, not(any(target_os = "linux", target_os = "macos"))))]
+fn mount_fs_type(_path: &Path) -> Option<String> {
+    None
+}
+
+/// Find the filesystem type of the longest mount point containing `path`
+/// in `/proc/mounts` formatted content
+#[cfg(any(target_os = "linux", test))]
+fn find_mount_fs_type(mounts: &str, path: &Path) -> Option<String> {
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let _device = fields.next()?;
+            // Spaces in mount points are octal-escaped
+            let mount_point = fields.next()?.replace("\\040", " ");
+            let fs_type = fields.next()?;
+            Some((PathBuf::from(mount_point), fs_type))
+        })
+        .filter(|(mount_point, _)| path.starts_with(mount_point))
+        .max_by_key(|(mount_point, _)| mount_point.as_os_str().len())
+        .map(|(_, fs_type)| fs_type.to_string())
+}
+
 /// Check if a path is writable by attempting to create a temp file
 async fn is_path_writable(path: &Path) -> bool {
     let test_dir = if path.exists() {
@@ -179,6 +350,167 @@ async fn is_path_writable(path: &Path) -> bool {
     }
 }
 
+// ============================================================================
+// Source index and journal
+// ============================================================================
+
+/// A regular file scheduled for migration
+#[derive(Debug, Clone)]
+struct SourceEntry {
+    relative: PathBuf,
+    /// Platform-independent relative path used as the journal key
+    key: String,
+    size: u64,
+}
+
+/// Snapshot of the source directory taken before copying
+#[derive(Debug, Default)]
+struct SourceIndex {
+    dirs: Vec<PathBuf>,
+    files: Vec<SourceEntry>,
+    total_bytes: u64,
+}
+
+fn journal_key(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn build_source_index_sync(
+    root: &Path,
+    relative: &Path,
+    index: &mut SourceIndex,
+) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(root.join(relative))? {
+        let entry = entry?;
+        if relative.as_os_str().is_empty() && entry.file_name() == MIGRATION_JOURNAL_FILE {
+            continue;
+        }
+        let file_type = entry.file_type()?;
+        let rel = relative.join(entry.file_name());
+        if file_type.is_dir() {
+            index.dirs.push(rel.clone());
+            build_source_index_sync(root, &rel, index)?;
+        } else if file_type.is_file() {
+            let size = entry.metadata()?.len();
+            index.total_bytes += size;
+            index.files.push(SourceEntry {
+                key: journal_key(&rel),
+                relative: rel,
+                size,
+            });
+        }
+        // Skip symlinks for safety
+    }
+    Ok(())
+}
+
+async fn build_source_index(source: &Path) -> CogniaResult<SourceIndex> {
+    let source = source.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let mut index = SourceIndex::default();
+        build_source_index_sync(&source, Path::new(""), &mut index).map(|_| index)
+    })
+    .await
+    .map_err(|e| CogniaError::Internal(format!("Failed to index source: {}", e)))?
+    .map_err(CogniaError::Io)
+}
+
+/// One line of the migration journal (JSON lines)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum JournalRecord {
+    Start {
+        source: String,
+    },
+    Entry {
+        path: String,
+        size: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        sha256: Option<String>,
+    },
+}
+
+/// A file an earlier run recorded as copied
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct JournaledEntry {
+    size: u64,
+    sha256: Option<String>,
+}
+
+fn parse_journal(content: &str, source: &Path) -> HashMap<String, JournaledEntry> {
+    let source = source.display().to_string();
+    let mut entries = HashMap::new();
+    let mut matches_source = false;
+    for line in content.lines() {
+        match serde_json::from_str::<JournalRecord>(line) {
+            Ok(JournalRecord::Start { source: s }) => matches_source = s == source,
+            Ok(JournalRecord::Entry { path, size, sha256 }) if matches_source => {
+                entries.insert(path, JournaledEntry { size, sha256 });
+            }
+            // Entries of another source, or a torn line from an interrupted write
+            _ => {}
+        }
+    }
+    entries
+}
+
+/// Load the entries completed by a previous run migrating `source` to `destination`
+async fn load_journal(source: &Path, destination: &Path) -> HashMap<String, JournaledEntry> {
+    match tokio::fs::read_to_string(destination.join(MIGRATION_JOURNAL_FILE)).await {
+        Ok(content) => parse_journal(&content, source),
+        Err(_) => HashMap::new(),
+    }
+}
+
+/// Open the journal for appending, starting a fresh one when nothing can be resumed
+async fn open_journal(
+    source: &Path,
+    destination: &Path,
+    resume: bool,
+) -> CogniaResult<tokio::fs::File> {
+    let mut options = tokio::fs::OpenOptions::new();
+    options.create(true);
+    if resume {
+        options.append(true);
+    } else {
+        options.write(true).truncate(true);
+    }
+    let mut file = options
+        .open(destination.join(MIGRATION_JOURNAL_FILE))
+        .await
+        .map_err(CogniaError::Io)?;
+    if resume {
+        // Terminate a line torn by an interrupted write; blank lines are ignored
+        file.write_all(b"\n").await.map_err(CogniaError::Io)?;
+    } else {
+        append_journal(
+            &mut file,
+            &JournalRecord::Start {
+                source: source.display().to_string(),
+            },
+        )
+        .await?;
+    }
+    Ok(file)
+}
+
+async fn append_journal(file: &mut tokio::fs::File, record: &JournalRecord) -> CogniaResult<()> {
+    let mut line = serde_json::to_string(record).map_err(|e| CogniaError::Parse(e.to_string()))?;
+    line.push('\n');
+    file.write_all(line.as_bytes())
+        .await
+        .map_err(CogniaError::Io)?;
+    file.flush().await.map_err(CogniaError::Io)
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
 /// Validate migration parameters before executing
 pub async fn validate_migration(
     source: &Path,
@@ -202,6 +534,16 @@ pub async fn validate_migration(
         0
     };
 
+    // Entries of an interrupted migration do not need to be copied again
+    let journal = if destination_exists {
+        load_journal(source, destination).await
+    } else {
+        HashMap::new()
+    };
+    let resumable_files = journal.len();
+    let resumable_bytes: u64 = journal.values().map(|entry| entry.size).sum();
+    let required_space = source_size.saturating_sub(resumable_bytes);
+
     // Check destination writability
     let dest_check_path = if destination_exists {
         destination.to_path_buf()
@@ -217,7 +559,7 @@ pub async fn validate_migration(
     let (destination_space_available, has_enough_space) = if destination_writable {
         match disk::get_disk_space(&dest_check_path).await {
             Ok(space) => {
-                let enough = space.available > source_size + 1024 * 1024; // 1MB buffer
+                let enough = space.available > required_space + 1024 * 1024; // 1MB buffer
                 (space.available, enough)
             }
             Err(_) => (0, false),
@@ -227,6 +569,12 @@ pub async fn validate_migration(
     };
 
     let same_drive = is_same_drive(source, destination);
+    let network_drive = {
+        let destination = destination.to_path_buf();
+        tokio::task::spawn_blocking(move || is_network_path(&destination))
+            .await
+            .unwrap_or(false)
+    };
 
     // Validation checks
     if !source_exists {
@@ -248,20 +596,30 @@ pub async fn validate_migration(
         }
     }
 
+    if resumable_files > 0 {
+        warnings.push(format!(
+            "Resuming an interrupted migration: {} files ({}) already copied will be skipped",
+            resumable_files,
+            disk::format_size(resumable_bytes)
+        ));
+    }
+
     if !destination_writable {
         errors.push("Destination path is not writable".to_string());
     }
 
-    if !has_enough_space && source_size > 0 {
+    if !has_enough_space && required_space > 0 {
         errors.push(format!(
             "Insufficient disk space at destination: need {}, available {}",
-            disk::format_size(source_size),
+            disk::format_size(required_space),
             disk::format_size(destination_space_available)
         ));
     }
 
     if source == destination {
         errors.push("Source and destination paths are the same".to_string());
+    } else if destination.starts_with(source) {
+        errors.push("Destination cannot be inside the source cache directory".to_string());
     }
 
     // Check if source is a symlink already
@@ -279,6 +637,13 @@ pub async fn validate_migration(
             .push("Source and destination are on the same drive - move will be faster".to_string());
     }
 
+    if network_drive {
+        warnings.push(
+            "Destination is on a network drive - migration will be slower and the cache becomes unavailable while the share is disconnected"
+                .to_string(),
+        );
+    }
+
     let is_valid = errors.is_empty();
 
     Ok(MigrationValidation {
@@ -293,178 +658,444 @@ pub async fn validate_migration(
         destination_space_human: disk::format_size(destination_space_available),
         has_enough_space,
         is_same_drive: same_drive,
+        is_network_drive: network_drive,
+        resumable_files,
         errors,
         warnings,
     })
 }
 
-/// Copy directory recursively with size tracking
-fn copy_dir_recursive<'a>(
-    src: &'a Path,
-    dst: &'a Path,
-    bytes_copied: &'a mut u64,
-    files_count: &'a mut usize,
-) -> std::pin::Pin<Box<dyn std::future::Future<Output = CogniaResult<()>> + Send + 'a>> {
-    Box::pin(async move {
-        fs::create_dir_all(dst).await?;
-
-        let mut entries = tokio::fs::read_dir(src).await.map_err(CogniaError::Io)?;
-
-        while let Some(entry) = entries.next_entry().await.map_err(CogniaError::Io)? {
-            let src_path = entry.path();
-            let dst_path = dst.join(entry.file_name());
-
-            let file_type = entry.file_type().await.map_err(CogniaError::Io)?;
-
-            if file_type.is_dir() {
-                copy_dir_recursive(&src_path, &dst_path, bytes_copied, files_count).await?;
-            } else if file_type.is_file() {
-                tokio::fs::copy(&src_path, &dst_path)
-                    .await
-                    .map_err(CogniaError::Io)?;
-                let size = tokio::fs::metadata(&dst_path)
-                    .await
-                    .map(|m| m.len())
-                    .unwrap_or(0);
-                *bytes_copied += size;
-                *files_count += 1;
+// ============================================================================
+// Execution
+// ============================================================================
+
+/// Tracks copy counters and reports them through the progress callback
+struct ProgressTracker<'a, F> {
+    on_progress: &'a F,
+    total_bytes: u64,
+    total_files: usize,
+    bytes_copied: u64,
+    files_copied: usize,
+    files_skipped: usize,
+    last_report: Option<Instant>,
+}
+
+impl<'a, F> ProgressTracker<'a, F>
+where
+    F: Fn(&MigrationProgress),
+{
+    fn new(on_progress: &'a F) -> Self {
+        Self {
+            on_progress,
+            total_bytes: 0,
+            total_files: 0,
+            bytes_copied: 0,
+            files_copied: 0,
+            files_skipped: 0,
+            last_report: None,
+        }
+    }
+
+    fn report(&mut self, stage: MigrationStage, message: impl Into<String>, file: Option<&str>) {
+        let percent = if self.total_bytes == 0 {
+            if stage == MigrationStage::Complete {
+                100.0
+            } else {
+                0.0
             }
-            // Skip symlinks for safety
+        } else {
+            (self.bytes_copied as f64 / self.total_bytes as f64 * 100.0) as f32
+        };
+        (self.on_progress)(&MigrationProgress {
+            stage,
+            message: message.into(),
+            bytes_copied: self.bytes_copied,
+            total_bytes: self.total_bytes,
+            files_copied: self.files_copied,
+            files_skipped: self.files_skipped,
+            total_files: self.total_files,
+            current_file: file.map(str::to_string),
+            percent,
+        });
+        self.last_report = Some(Instant::now());
+    }
+
+    /// Report copy progress, throttled to [`PROGRESS_INTERVAL`]
+    fn report_copy(&mut self, file: &str) {
+        let due = self
+            .last_report
+            .map_or(true, |last| last.elapsed() >= PROGRESS_INTERVAL);
+        if due || self.files_copied == self.total_files {
+            self.report(
+                MigrationStage::Copying,
+                format!("Copied {} of {} files", self.files_copied, self.total_files),
+                Some(file),
+            );
         }
+    }
+}
 
-        Ok(())
-    })
+fn migration_result(
+    source: &Path,
+    destination: &Path,
+    mode: MigrationMode,
+    bytes_migrated: u64,
+    files_count: usize,
+    error: Option<String>,
+) -> MigrationResult {
+    MigrationResult {
+        success: error.is_none(),
+        mode: mode.as_str().to_string(),
+        source: source.display().to_string(),
+        destination: destination.display().to_string(),
+        bytes_migrated,
+        bytes_migrated_human: disk::format_size(bytes_migrated),
+        files_count,
+        files_skipped: 0,
+        symlink_created: false,
+        source_kept: false,
+        resumable: false,
+        error,
+    }
 }
 
-/// Execute cache migration
+/// Copy a single entry and verify it against the source index. Returns the
+/// hash of the copy, which the journal keeps so a resumed run can check it.
+async fn copy_entry(
+    source: &Path,
+    destination: &Path,
+    entry: &SourceEntry,
+    verify_hashes: bool,
+) -> CogniaResult<String> {
+    let src_path = source.join(&entry.relative);
+    let dst_path = destination.join(&entry.relative);
+
+    tokio::fs::copy(&src_path, &dst_path)
+        .await
+        .map_err(CogniaError::Io)?;
+
+    let copied_size = tokio::fs::metadata(&dst_path)
+        .await
+        .map(|m| m.len())
+        .map_err(CogniaError::Io)?;
+    if copied_size != entry.size {
+        let _ = tokio::fs::remove_file(&dst_path).await;
+        return Err(CogniaError::Internal(format!(
+            "size mismatch for {}: expected {}, copied {}",
+            entry.key, entry.size, copied_size
+        )));
+    }
+
+    let copied_hash = fs::calculate_sha256(&dst_path).await?;
+    if verify_hashes && fs::calculate_sha256(&src_path).await? != copied_hash {
+        let _ = tokio::fs::remove_file(&dst_path).await;
+        return Err(CogniaError::Internal(format!(
+            "checksum mismatch for {}",
+            entry.key
+        )));
+    }
+    Ok(copied_hash)
+}
+
+/// Whether a copy recorded by an earlier run is still intact at the
+/// destination. The recorded hash is always checked, and `verify_hashes`
+/// also compares against the source.
+async fn journaled_copy_intact(
+    source: &Path,
+    destination: &Path,
+    entry: &SourceEntry,
+    journaled: Option<&JournaledEntry>,
+    verify_hashes: bool,
+) -> CogniaResult<bool> {
+    let Some(journaled) = journaled.filter(|journaled| journaled.size == entry.size) else {
+        return Ok(false);
+    };
+    let dst_path = destination.join(&entry.relative);
+    if !tokio::fs::metadata(&dst_path)
+        .await
+        .is_ok_and(|m| m.len() == entry.size)
+    {
+        return Ok(false);
+    }
+    if journaled.sha256.is_none() && !verify_hashes {
+        return Ok(true);
+    }
+
+    let Ok(actual) = fs::calculate_sha256(&dst_path).await else {
+        return Ok(false);
+    };
+    if journaled
+        .sha256
+        .as_deref()
+        .is_some_and(|expected| !expected.eq_ignore_ascii_case(&actual))
+    {
+        return Ok(false);
+    }
+    if verify_hashes {
+        return Ok(fs::calculate_sha256(&source.join(&entry.relative)).await? == actual);
+    }
+    Ok(true)
+}
+
+/// Copy the source cache to the destination and verify every entry.
 ///
-/// Mode::Move: Move files, update config path
-/// Mode::MoveAndLink: Move files, create symlink at old location
-pub async fn migrate_cache(
+/// The source is left untouched, so the caller can switch the configured
+/// cache path before calling [`complete_migration`]. On failure the journal is
+/// kept at the destination and a re-run skips the entries already copied.
+pub async fn copy_and_verify<F>(
     source: &Path,
     destination: &Path,
     mode: MigrationMode,
-) -> CogniaResult<MigrationResult> {
+    options: &MigrationOptions,
+    on_progress: &F,
+) -> CogniaResult<MigrationResult>
+where
+    F: Fn(&MigrationProgress) + Send + Sync,
+{
+    let mut tracker = ProgressTracker::new(on_progress);
+
     // Step 1: Validate
+    tracker.report(MigrationStage::Validating, "Validating migration", None);
+    if options.keep_source && mode == MigrationMode::MoveAndLink {
+        let error = "keep_source cannot be combined with move_and_link: the link replaces the source directory";
+        tracker.report(MigrationStage::Failed, error, None);
+        return Ok(migration_result(
+            source,
+            destination,
+            mode,
+            0,
+            0,
+            Some(error.to_string()),
+        ));
+    }
     let validation = validate_migration(source, destination).await?;
     if !validation.is_valid {
-        return Ok(MigrationResult {
-            success: false,
-            mode: format!("{:?}", mode).to_lowercase(),
-            source: source.display().to_string(),
-            destination: destination.display().to_string(),
-            bytes_migrated: 0,
-            bytes_migrated_human: "0 B".to_string(),
-            files_count: 0,
-            symlink_created: false,
-            error: Some(validation.errors.join("; ")),
-        });
+        let error = validation.errors.join("; ");
+        tracker.report(MigrationStage::Failed, error.clone(), None);
+        return Ok(migration_result(
+            source,
+            destination,
+            mode,
+            0,
+            0,
+            Some(error),
+        ));
     }
 
-    // Step 2: Create destination directory
+    // Step 2: Index the source
+    tracker.report(MigrationStage::Calculating, "Indexing source cache", None);
+    let index = build_source_index(source).await?;
+    tracker.total_bytes = index.total_bytes;
+    tracker.total_files = index.files.len();
+
+    // Step 3: Prepare destination and journal
     fs::create_dir_all(destination).await?;
+    let completed = load_journal(source, destination).await;
+    let mut journal = open_journal(source, destination, !completed.is_empty()).await?;
 
-    // Step 3: Copy files
-    let mut bytes_copied = 0u64;
-    let mut files_count = 0usize;
+    // Step 4: Copy entries, skipping those recorded by an interrupted run
+    let copy_result = async {
+        for dir in &index.dirs {
+            fs::create_dir_all(destination.join(dir)).await?;
+        }
 
-    if let Err(e) =
-        copy_dir_recursive(source, destination, &mut bytes_copied, &mut files_count).await
-    {
-        // Cleanup on failure
-        let _ = fs::remove_dir_all(destination).await;
-        return Ok(MigrationResult {
-            success: false,
-            mode: format!("{:?}", mode).to_lowercase(),
-            source: source.display().to_string(),
-            destination: destination.display().to_string(),
-            bytes_migrated: bytes_copied,
-            bytes_migrated_human: disk::format_size(bytes_copied),
-            files_count,
-            symlink_created: false,
-            error: Some(format!("Copy failed: {}", e)),
-        });
+        for entry in &index.files {
+            if options.is_cancelled() {
+                return Err(CogniaError::Cancelled);
+            }
+            let already_copied = journaled_copy_intact(
+                source,
+                destination,
+                entry,
+                completed.get(&entry.key),
+                options.verify_hashes,
+            )
+            .await?;
+
+            if already_copied {
+                tracker.files_skipped += 1;
+            } else {
+                let sha256 = copy_entry(source, destination, entry, options.verify_hashes).await?;
+                append_journal(
+                    &mut journal,
+                    &JournalRecord::Entry {
+                        path: entry.key.clone(),
+                        size: entry.size,
+                        sha256: Some(sha256),
+                    },
+                )
+                .await?;
+            }
+
+            tracker.bytes_copied += entry.size;
+            tracker.files_copied += 1;
+            tracker.report_copy(&entry.key);
+        }
+        Ok::<(), CogniaError>(())
+    }
+    .await;
+    drop(journal);
+
+    if let Err(e) = copy_result {
+        let error = format!(
+            "Copy failed: {}. Progress was saved; run the migration again to resume.",
+            e
+        );
+        tracker.report(MigrationStage::Failed, error.clone(), None);
+        let mut result = migration_result(
+            source,
+            destination,
+            mode,
+            tracker.bytes_copied,
+            tracker.files_copied,
+            Some(error),
+        );
+        result.files_skipped = tracker.files_skipped;
+        result.resumable = true;
+        return Ok(result);
     }
 
-    // Step 4: Verify basic integrity (compare total sizes)
-    let dest_size = dir_size(destination).await;
-    let size_diff = validation.source_size.abs_diff(dest_size);
-
-    // Allow 1% tolerance for filesystem overhead differences
-    let tolerance = validation.source_size / 100;
-    if size_diff > tolerance.max(4096) {
-        let _ = fs::remove_dir_all(destination).await;
-        return Ok(MigrationResult {
-            success: false,
-            mode: format!("{:?}", mode).to_lowercase(),
-            source: source.display().to_string(),
-            destination: destination.display().to_string(),
-            bytes_migrated: bytes_copied,
-            bytes_migrated_human: disk::format_size(bytes_copied),
-            files_count,
-            symlink_created: false,
-            error: Some(format!(
-                "Verification failed: source {} vs destination {}",
-                disk::format_size(validation.source_size),
-                disk::format_size(dest_size)
-            )),
-        });
+    // Step 5: Verify the destination against the source index
+    tracker.report(MigrationStage::Verifying, "Verifying copied files", None);
+    for entry in &index.files {
+        let size = tokio::fs::metadata(destination.join(&entry.relative))
+            .await
+            .map(|m| m.len())
+            .ok();
+        if size != Some(entry.size) {
+            let error = format!(
+                "Verification failed: {} expected {}, found {}. Run the migration again to re-copy it.",
+                entry.key,
+                disk::format_size(entry.size),
+                size.map(disk::format_size)
+                    .unwrap_or_else(|| "missing".to_string())
+            );
+            tracker.report(MigrationStage::Failed, error.clone(), None);
+            let mut result = migration_result(
+                source,
+                destination,
+                mode,
+                tracker.bytes_copied,
+                tracker.files_copied,
+                Some(error),
+            );
+            result.files_skipped = tracker.files_skipped;
+            result.resumable = true;
+            return Ok(result);
+        }
     }
 
-    // Step 5: Remove old cache
+    let mut result = migration_result(
+        source,
+        destination,
+        mode,
+        index.total_bytes,
+        index.files.len(),
+        None,
+    );
+    result.files_skipped = tracker.files_skipped;
+    Ok(result)
+}
+
+/// Finish a migration whose data was copied by [`copy_and_verify`].
+///
+/// Removes the journal, then deletes the source (unless `keep_source`) and
+/// creates the symlink for [`MigrationMode::MoveAndLink`].
+pub async fn complete_migration<F>(
+    source: &Path,
+    destination: &Path,
+    mode: MigrationMode,
+    options: &MigrationOptions,
+    mut result: MigrationResult,
+    on_progress: &F,
+) -> MigrationResult
+where
+    F: Fn(&MigrationProgress) + Send + Sync,
+{
+    let mut tracker = ProgressTracker::new(on_progress);
+    tracker.total_bytes = result.bytes_migrated;
+    tracker.bytes_copied = result.bytes_migrated;
+    tracker.total_files = result.files_count;
+    tracker.files_copied = result.files_count;
+    tracker.files_skipped = result.files_skipped;
+
+    let _ = tokio::fs::remove_file(destination.join(MIGRATION_JOURNAL_FILE)).await;
+
+    if options.keep_source {
+        result.source_kept = true;
+        tracker.report(MigrationStage::Complete, "Migration complete", None);
+        return result;
+    }
+
+    // Remove old cache
+    tracker.report(MigrationStage::CleaningUp, "Removing source cache", None);
     if let Err(e) = fs::remove_dir_all(source).await {
-        return Ok(MigrationResult {
-            success: false,
-            mode: format!("{:?}", mode).to_lowercase(),
-            source: source.display().to_string(),
-            destination: destination.display().to_string(),
-            bytes_migrated: bytes_copied,
-            bytes_migrated_human: disk::format_size(bytes_copied),
-            files_count,
-            symlink_created: false,
-            error: Some(format!("Failed to remove source directory: {}", e)),
-        });
+        let error = format!("Failed to remove source directory: {}", e);
+        tracker.report(MigrationStage::Failed, error.clone(), None);
+        result.success = false;
+        result.source_kept = true;
+        result.error = Some(error);
+        return result;
     }
 
-    // Step 6: Create symlink if MoveAndLink mode
-    let symlink_created = if mode == MigrationMode::MoveAndLink {
-        match fs::create_symlink(destination, source).await {
-            Ok(_) => true,
-            Err(e) => {
-                return Ok(MigrationResult {
-                    success: false,
-                    mode: "move_and_link".to_string(),
-                    source: source.display().to_string(),
-                    destination: destination.display().to_string(),
-                    bytes_migrated: bytes_copied,
-                    bytes_migrated_human: disk::format_size(bytes_copied),
-                    files_count,
-                    symlink_created: false,
-                    error: Some(format!(
-                        "Files moved but symlink creation failed: {}. Data is at new location.",
-                        e
-                    )),
-                });
-            }
+    // Create symlink if MoveAndLink mode
+    if mode == MigrationMode::MoveAndLink {
+        tracker.report(MigrationStage::CreatingLink, "Creating symlink", None);
+        if let Err(e) = fs::create_symlink(destination, source).await {
+            let error = format!(
+                "Files moved but symlink creation failed: {}. Data is at new location.",
+                e
+            );
+            tracker.report(MigrationStage::Failed, error.clone(), None);
+            result.success = false;
+            result.error = Some(error);
+            return result;
         }
-    } else {
-        false
-    };
+        result.symlink_created = true;
+    }
 
-    Ok(MigrationResult {
-        success: true,
-        mode: match mode {
-            MigrationMode::Move => "move".to_string(),
-            MigrationMode::MoveAndLink => "move_and_link".to_string(),
-        },
-        source: source.display().to_string(),
-        destination: destination.display().to_string(),
-        bytes_migrated: bytes_copied,
-        bytes_migrated_human: disk::format_size(bytes_copied),
-        files_count,
-        symlink_created,
-        error: None,
-    })
+    tracker.report(MigrationStage::Complete, "Migration complete", None);
+    result
+}
+
+/// Execute cache migration
+///
+/// Mode::Move: Move files, update config path
+/// Mode::MoveAndLink: Move files, create symlink at old location
+pub async fn migrate_cache(
+    source: &Path,
+    destination: &Path,
+    mode: MigrationMode,
+) -> CogniaResult<MigrationResult> {
+    migrate_cache_with_options(
+        source,
+        destination,
+        mode,
+        &MigrationOptions::default(),
+        &|_: &MigrationProgress| {},
+    )
+    .await
+}
+
+/// Execute cache migration with options and a progress callback.
+///
+/// Does not touch settings; callers that switch the configured cache path
+/// should use [`copy_and_verify`] and [`complete_migration`] directly.
+pub async fn migrate_cache_with_options<F>(
+    source: &Path,
+    destination: &Path,
+    mode: MigrationMode,
+    options: &MigrationOptions,
+    on_progress: &F,
+) -> CogniaResult<MigrationResult>
+where
+    F: Fn(&MigrationProgress) + Send + Sync,
+{
+    let result = copy_and_verify(source, destination, mode, options, on_progress).await?;
+    if !result.success {
+        return Ok(result);
+    }
+    Ok(complete_migration(source, destination, mode, options, result, on_progress).await)
 }
 
 #[cfg(test)]
@@ -695,4 +1326,261 @@ fn test_migration_mode_serde() {
         let mode2: MigrationMode = serde_json::from_str("\"move_and_link\"").unwrap();
         assert_eq!(mode2, MigrationMode::MoveAndLink);
     }
+
+    #[tokio::test]
+    async fn test_migrate_resumes_from_journal() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("resume_src");
+        let dst = dir.path().join("resume_dst");
+
+        tokio::fs::create_dir_all(&src).await.unwrap();
+        tokio::fs::write(src.join("a.txt"), b"hello").await.unwrap();
+        tokio::fs::write(src.join("b.txt"), b"world!")
+            .await
+            .unwrap();
+
+        // Simulate an interrupted run that already copied a.txt
+        tokio::fs::create_dir_all(&dst).await.unwrap();
+        tokio::fs::write(dst.join("a.txt"), b"HELLO").await.unwrap();
+        let journal = format!(
+            "{}\n{}\n{{\"kind\":\"entry\",\"pa",
+            serde_json::json!({"kind": "start", "source": src.display().to_string()}),
+            serde_json::json!({"kind": "entry", "path": "a.txt", "size": 5}),
+        );
+        tokio::fs::write(dst.join(MIGRATION_JOURNAL_FILE), journal)
+            .await
+            .unwrap();
+
+        let validation = validate_migration(&src, &dst).await.unwrap();
+        assert_eq!(validation.resumable_files, 1);
+
+        let result = migrate_cache(&src, &dst, MigrationMode::Move)
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.files_count, 2);
+        assert_eq!(result.files_skipped, 1);
+        assert_eq!(result.bytes_migrated, 11);
+        // The journaled entry was not copied again
+        assert_eq!(tokio::fs::read(dst.join("a.txt")).await.unwrap(), b"HELLO");
+        assert_eq!(tokio::fs::read(dst.join("b.txt")).await.unwrap(), b"world!");
+        assert!(!dst.join(MIGRATION_JOURNAL_FILE).exists());
+        assert!(!src.exists());
+    }
+
+    #[tokio::test]
+    async fn test_migrate_recopies_journaled_file_with_wrong_hash() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("corrupt_src");
+        let dst = dir.path().join("corrupt_dst");
+
+        tokio::fs::create_dir_all(&src).await.unwrap();
+        tokio::fs::write(src.join("a.txt"), b"hello").await.unwrap();
+
+        // The journal recorded the hash of the intact copy, but the file at
+        // the destination was corrupted afterwards without changing its size
+        tokio::fs::create_dir_all(&dst).await.unwrap();
+        tokio::fs::write(dst.join("a.txt"), b"hxllo").await.unwrap();
+        let sha256 = fs::calculate_sha256(&src.join("a.txt")).await.unwrap();
+        let journal = format!(
+            "{}\n{}\n",
+            serde_json::json!({"kind": "start", "source": src.display().to_string()}),
+            serde_json::json!({"kind": "entry", "path": "a.txt", "size": 5, "sha256": sha256}),
+        );
+        tokio::fs::write(dst.join(MIGRATION_JOURNAL_FILE), journal)
+            .await
+            .unwrap();
+
+        let result = migrate_cache(&src, &dst, MigrationMode::Move)
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.files_skipped, 0);
+        assert_eq!(tokio::fs::read(dst.join("a.txt")).await.unwrap(), b"hello");
+    }
+
+    #[tokio::test]
+    async fn test_migrate_keep_source() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("keep_src");
+        let dst = dir.path().join("keep_dst");
+
+        tokio::fs::create_dir_all(src.join("sub")).await.unwrap();
+        tokio::fs::write(src.join("sub").join("f.bin"), b"payload")
+            .await
+            .unwrap();
+
+        let options = MigrationOptions {
+            keep_source: true,
+            verify_hashes: true,
+            cancel: None,
+        };
+        let result = migrate_cache_with_options(
+            &src,
+            &dst,
+            MigrationMode::Move,
+            &options,
+            &|_: &MigrationProgress| {},
+        )
+        .await
+        .unwrap();
+        assert!(result.success);
+        assert!(result.source_kept);
+        assert!(src.join("sub").join("f.bin").exists());
+        assert!(dst.join("sub").join("f.bin").exists());
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_migration_is_resumable() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("cancel_src");
+        let dst = dir.path().join("cancel_dst");
+        tokio::fs::create_dir_all(&src).await.unwrap();
+        tokio::fs::write(src.join("a.bin"), b"a").await.unwrap();
+
+        let options = MigrationOptions {
+            cancel: Some(Arc::new(AtomicBool::new(true))),
+            ..Default::default()
+        };
+        let result = copy_and_verify(
+            &src,
+            &dst,
+            MigrationMode::Move,
+            &options,
+            &|_: &MigrationProgress| {},
+        )
+        .await
+        .unwrap();
+        assert!(!result.success);
+        assert!(result.resumable);
+        assert!(!dst.join("a.bin").exists());
+        assert!(src.join("a.bin").exists());
+    }
+
+    #[tokio::test]
+    async fn test_keep_source_rejected_with_link() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("link_keep_src");
+        let dst = dir.path().join("link_keep_dst");
+        tokio::fs::create_dir_all(&src).await.unwrap();
+
+        let options = MigrationOptions {
+            keep_source: true,
+            verify_hashes: false,
+            cancel: None,
+        };
+        let result = migrate_cache_with_options(
+            &src,
+            &dst,
+            MigrationMode::MoveAndLink,
+            &options,
+            &|_: &MigrationProgress| {},
+        )
+        .await
+        .unwrap();
+        assert!(!result.success);
+        assert!(src.exists());
+    }
+
+    #[tokio::test]
+    async fn test_migrate_reports_progress() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("progress_src");
+        let dst = dir.path().join("progress_dst");
+        tokio::fs::create_dir_all(&src).await.unwrap();
+        tokio::fs::write(src.join("one.txt"), b"1").await.unwrap();
+        tokio::fs::write(src.join("two.txt"), b"22").await.unwrap();
+
+        let events = std::sync::Mutex::new(Vec::new());
+        let result = migrate_cache_with_options(
+            &src,
+            &dst,
+            MigrationMode::Move,
+            &MigrationOptions::default(),
+            &|p: &MigrationProgress| events.lock().unwrap().push(p.clone()),
+        )
+        .await
+        .unwrap();
+        assert!(result.success);
+
+        let events = events.into_inner().unwrap();
+        let copying = events
+            .iter()
+            .rfind(|p| p.stage == MigrationStage::Copying)
+            .unwrap();
+        assert_eq!(copying.files_copied, 2);
+        assert_eq!(copying.total_files, 2);
+        assert_eq!(copying.bytes_copied, 3);
+        let last = events.last().unwrap();
+        assert_eq!(last.stage, MigrationStage::Complete);
+        assert_eq!(last.percent, 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_validate_destination_inside_source() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("outer");
+        tokio::fs::create_dir_all(&src).await.unwrap();
+
+        let result = validate_migration(&src, &src.join("inner")).await.unwrap();
+        assert!(!result.is_valid);
+        assert!(result.errors.iter().any(|e| e.contains("inside")));
+    }
+
+    #[test]
+    fn test_parse_journal_filters_source() {
+        let content = [
+            r#"{"kind":"start","source":"/old"}"#,
+            r#"{"kind":"entry","path":"stale.txt","size":1}"#,
+            r#"{"kind":"start","source":"/cache"}"#,
+            r#"{"kind":"entry","path":"a/b.txt","size":42,"sha256":"abc"}"#,
+            r#"{"kind":"entry","pa"#,
+        ]
+        .join("\n");
+        let entries = parse_journal(&content, Path::new("/cache"));
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            entries.get("a/b.txt"),
+            Some(&JournaledEntry {
+                size: 42,
+                sha256: Some("abc".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_find_mount_fs_type() {
+        let mounts = "\
+/dev/sda1 / ext4 rw,relatime 0 0
+server:/export /mnt/nfs nfs4 rw,relatime 0 0
+//nas/share /mnt/my\\040share cifs rw 0 0
+";
+        assert_eq!(
+            find_mount_fs_type(mounts, Path::new("/mnt/nfs/cache")).as_deref(),
+            Some("nfs4")
+        );
+        assert_eq!(
+            find_mount_fs_type(mounts, Path::new("/mnt/my share/cache")).as_deref(),
+            Some("cifs")
+        );
+        assert_eq!(
+            find_mount_fs_type(mounts, Path::new("/home/user")).as_deref(),
+            Some("ext4")
+        );
+        // Prefix matching is per path component
+        assert_eq!(
+            find_mount_fs_type(mounts, Path::new("/mnt/nfsx")).as_deref(),
+            Some("ext4")
+        );
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_is_network_fs_type() {
+        assert!(is_network_fs_type("nfs4"));
+        assert!(is_network_fs_type("CIFS"));
+        assert!(is_network_fs_type("fuse.sshfs"));
+        assert!(!is_network_fs_type("ext4"));
+        assert!(!is_network_fs_type("fuse.portal"));
+    }
 }
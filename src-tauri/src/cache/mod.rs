@@ -1,12 +1,15 @@
+pub mod analytics;
 pub mod db;
 pub mod download;
 pub mod download_history;
 pub mod download_resumer;
 pub mod external;
+pub mod external_preview;
 pub mod history;
 pub mod metadata;
 pub mod migration;
 pub mod sqlite_db;
+pub mod verify;
 
 use std::sync::Arc;
 
@@ -15,8 +18,13 @@
 // Re-export main types (avoiding glob conflicts)
 pub use db::{CacheEntry, CacheEntryType, CacheIndex, CacheStats};
 pub use download::DownloadCache;
-pub use download_history::{DownloadHistory, DownloadRecord, DownloadStatus, HistoryStats};
-pub use download_resumer::{DownloadResumer, PartialDownload};
+pub use download_history::{
+    DownloadHistory, DownloadRecord, DownloadStatus, HistoryExportFormat, HistoryFilter,
+    HistoryImportResult, HistoryRetention, HistoryStats,
+};
+pub use download_resumer::{
+    DownloadResumer, JournalCheckpoint, PartialDownload, PartialStatus, PartialValidation,
+};
 pub use external::{
     CacheScanProgress, CombinedCacheStats, ExternalCacheCleanResult, ExternalCacheInfo,
     ExternalCacheProvider, ScanConfig, ScanPhase, ScanPreset, ScanPresetInfo, ScanProviderProgress,
@@ -24,9 +32,14 @@
 };
 pub use history::{
     CleanedFileInfo, CleanupHistory, CleanupRecord, CleanupRecordBuilder, CleanupSummary,
+    MonthlyCleanupSummary,
 };
 pub use metadata::{CachedMetadata, MetadataCache, MetadataCacheStats};
-pub use migration::{MigrationMode, MigrationResult, MigrationValidation};
+pub use migration::{
+    MigrationMode, MigrationOptions, MigrationProgress, MigrationResult, MigrationValidation,
+};
 pub use sqlite_db::{
-    CacheAccessStats, CacheSizeSnapshot, DatabaseInfo, IntegrityCheckResult, SqliteCacheDb,
+    CacheAccessStats, CacheReference, CacheSizeSnapshot, CacheUsageSample, DatabaseInfo,
+    IntegrityCheckResult, SourceAccessStats, SqliteCacheDb,
 };
+pub use verify::{Quarantine, QuarantineEntry, VerifyProgress, VerifyState};
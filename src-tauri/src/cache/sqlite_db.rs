@@ -3,9 +3,10 @@
 use chrono::{DateTime, Duration as ChronoDuration, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{sqlite::SqlitePoolOptions, FromRow, SqlitePool};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
 
 use super::db::{CacheEntry, CacheEntryType, CacheStats};
 
@@ -31,6 +32,15 @@ fn default() -> Self {
     }
 }
 
+/// Hit/miss counters for one provider or cache namespace
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceAccessStats {
+    pub source: String,
+    pub hits: u64,
+    pub misses: u64,
+    pub hit_rate: f64,
+}
+
 /// SQLite-based cache database for improved performance and ACID compliance
 pub struct SqliteCacheDb {
     pool: SqlitePool,
@@ -38,6 +48,8 @@ pub struct SqliteCacheDb {
     // In-memory stats counters for performance (persisted periodically)
     stats_hits: AtomicU64,
     stats_misses: AtomicU64,
+    // Per-source (hits, misses) not yet added to `cache_access_by_source`
+    pending_by_source: Mutex<HashMap<String, (u64, u64)>>,
 }
 
 #[derive(Debug, FromRow)]
@@ -124,6 +136,23 @@ pub async fn open(cache_dir: &Path) -> CogniaResult<Self> {
         .await
         .map_err(|e| CogniaError::Internal(format!("Failed to create stats table: {}", e)))?;
 
+        // Per provider/source hit/miss counters, flushed in batches like the
+        // totals above and when the handle is dropped
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS cache_access_by_source (
+                source TEXT PRIMARY KEY,
+                hits INTEGER NOT NULL DEFAULT 0,
+                misses INTEGER NOT NULL DEFAULT 0
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| {
+            CogniaError::Internal(format!("Failed to create source stats table: {}", e))
+        })?;
+
         // Initialize stats row if not exists
         sqlx::query(
             r#"
@@ -173,6 +202,60 @@ pub async fn open(cache_dir: &Path) -> CogniaResult<Self> {
         .await
         .map_err(|e| CogniaError::Internal(format!("Failed to create snapshot index: {}", e)))?;
 
+        // Samples taken by the cache size monitor, including external caches,
+        // used to project cache growth
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS cache_usage_samples (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp TEXT NOT NULL,
+                internal_size INTEGER NOT NULL,
+                external_size INTEGER,
+                total_size INTEGER NOT NULL
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| {
+            CogniaError::Internal(format!("Failed to create usage samples table: {}", e))
+        })?;
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_usage_sample_ts ON cache_usage_samples(timestamp)",
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| {
+            CogniaError::Internal(format!("Failed to create usage sample index: {}", e))
+        })?;
+
+        // Installed environment versions built from a cached artifact. Keyed by
+        // checksum so every key aliasing the same file is covered.
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS cache_references (
+                checksum TEXT NOT NULL,
+                env_type TEXT NOT NULL,
+                version TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (checksum, env_type, version)
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| CogniaError::Internal(format!("Failed to create references table: {}", e)))?;
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_cache_references_env ON cache_references(env_type, version)",
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| {
+            CogniaError::Internal(format!("Failed to create references index: {}", e))
+        })?;
+
         // Load persisted stats
         let (hits, misses) = Self::load_stats_from_db(&pool).await.unwrap_or((0, 0));
 
@@ -181,6 +264,7 @@ pub async fn open(cache_dir: &Path) -> CogniaResult<Self> {
             cache_dir: cache_dir.to_path_buf(),
             stats_hits: AtomicU64::new(hits),
             stats_misses: AtomicU64::new(misses),
+            pending_by_source: Mutex::new(HashMap::new()),
         };
 
         let json_path = db.cache_dir.join("cache-index.json");
@@ -225,7 +309,7 @@ pub async fn persist_stats(&self) -> CogniaResult<()> {
             .await
             .map_err(|e| CogniaError::Internal(e.to_string()))?;
 
-        Ok(())
+        Self::write_source_deltas(&self.pool, self.take_source_deltas()).await
     }
 
     /// Get current access statistics
@@ -252,6 +336,7 @@ pub fn get_access_stats(&self) -> CacheAccessStats {
     pub async fn reset_access_stats(&self) -> CogniaResult<()> {
         self.stats_hits.store(0, Ordering::Relaxed);
         self.stats_misses.store(0, Ordering::Relaxed);
+        self.take_source_deltas();
 
         sqlx::query(
             "UPDATE cache_access_stats SET hits = 0, misses = 0, last_reset = ? WHERE id = 1",
@@ -261,9 +346,150 @@ pub async fn reset_access_stats(&self) -> CogniaResult<()> {
         .await
         .map_err(|e| CogniaError::Internal(e.to_string()))?;
 
+        sqlx::query("DELETE FROM cache_access_by_source")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| CogniaError::Internal(e.to_string()))?;
+
         Ok(())
     }
 
+    /// Hit/miss counters grouped by provider or cache namespace, busiest first
+    pub async fn access_stats_by_source(&self) -> CogniaResult<Vec<SourceAccessStats>> {
+        #[derive(FromRow)]
+        struct SourceRow {
+            source: String,
+            hits: i64,
+            misses: i64,
+        }
+
+        Self::write_source_deltas(&self.pool, self.take_source_deltas()).await?;
+        let rows: Vec<SourceRow> = sqlx::query_as(
+            "SELECT source, hits, misses FROM cache_access_by_source \
+             ORDER BY hits + misses DESC, source ASC",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| CogniaError::Internal(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| {
+                let hits = r.hits.max(0) as u64;
+                let misses = r.misses.max(0) as u64;
+                let total = hits + misses;
+                SourceAccessStats {
+                    source: r.source,
+                    hits,
+                    misses,
+                    hit_rate: if total > 0 {
+                        hits as f64 / total as f64
+                    } else {
+                        0.0
+                    },
+                }
+            })
+            .collect())
+    }
+
+    /// Source a lookup is attributed to when the caller does not tag it:
+    /// the namespace of metadata keys (`metadata:npm:...` -> `npm`),
+    /// `download` for checksum keys, otherwise the key's first segment.
+    pub fn access_source_for_key(key: &str) -> String {
+        let mut parts = key.splitn(3, ':');
+        let first = parts.next().unwrap_or_default();
+        let second = parts.next();
+        let has_rest = parts.next().is_some();
+        match (first, second) {
+            ("metadata", Some(ns)) if has_rest && !ns.is_empty() => ns.to_string(),
+            ("metadata", _) => "metadata".to_string(),
+            ("checksum", _) => "download".to_string(),
+            _ if second.is_some() && !first.is_empty() => first.to_string(),
+            _ => "other".to_string(),
+        }
+    }
+
+    /// Count a lookup in memory; per-source counters are written every 100
+    /// lookups, so lookups never wait on a database write
+    fn record_access(&self, source: &str, hit: bool) {
+        if hit {
+            self.record_hit();
+        } else {
+            self.record_miss();
+        }
+
+        let mut pending = self
+            .pending_by_source
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let counts = pending.entry(source.to_string()).or_default();
+        if hit {
+            counts.0 += 1;
+        } else {
+            counts.1 += 1;
+        }
+        let total: u64 = pending.values().map(|(hits, misses)| hits + misses).sum();
+        if total >= 100 {
+            self.spawn_source_flush(std::mem::take(&mut *pending));
+        }
+    }
+
+    fn take_source_deltas(&self) -> HashMap<String, (u64, u64)> {
+        std::mem::take(
+            &mut *self
+                .pending_by_source
+                .lock()
+                .unwrap_or_else(|e| e.into_inner()),
+        )
+    }
+
+    fn spawn_source_flush(&self, deltas: HashMap<String, (u64, u64)>) {
+        if deltas.is_empty() {
+            return;
+        }
+        let pool = self.pool.clone();
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            handle.spawn(async move {
+                if let Err(e) = Self::write_source_deltas(&pool, deltas).await {
+                    log::debug!("Failed to record cache access by source: {}", e);
+                }
+            });
+        }
+    }
+
+    /// Add per-source hit/miss deltas to `cache_access_by_source`
+    async fn write_source_deltas(
+        pool: &SqlitePool,
+        deltas: HashMap<String, (u64, u64)>,
+    ) -> CogniaResult<()> {
+        if deltas.is_empty() {
+            return Ok(());
+        }
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| CogniaError::Internal(e.to_string()))?;
+        for (source, (hits, misses)) in deltas {
+            sqlx::query(
+                r#"
+                INSERT INTO cache_access_by_source (source, hits, misses) VALUES (?, ?, ?)
+                ON CONFLICT(source) DO UPDATE SET
+                    hits = hits + excluded.hits,
+                    misses = misses + excluded.misses
+                "#,
+            )
+            .bind(source)
+            .bind(hits as i64)
+            .bind(misses as i64)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| CogniaError::Internal(e.to_string()))?;
+        }
+        tx.commit()
+            .await
+            .map_err(|e| CogniaError::Internal(e.to_string()))
+    }
+
     /// Record a cache hit (auto-persists every 100 operations)
     fn record_hit(&self) {
         let val = self.stats_hits.fetch_add(1, Ordering::Relaxed);
@@ -332,6 +558,16 @@ pub async fn migrate_from_json(&self, json_path: &Path) -> CogniaResult<usize> {
 
     /// Get a cache entry by key (tracks hit/miss statistics)
     pub async fn get(&self, key: &str) -> CogniaResult<Option<CacheEntry>> {
+        self.get_for_source(key, &Self::access_source_for_key(key))
+            .await
+    }
+
+    /// Get a cache entry by key, attributing the hit/miss to `source`
+    pub async fn get_for_source(
+        &self,
+        key: &str,
+        source: &str,
+    ) -> CogniaResult<Option<CacheEntry>> {
         let row: Option<CacheEntryRow> =
             sqlx::query_as("SELECT * FROM cache_entries WHERE key = ?")
                 .bind(key)
@@ -339,17 +575,22 @@ pub async fn get(&self, key: &str) -> CogniaResult<Option<CacheEntry>> {
                 .await
                 .map_err(|e| CogniaError::Internal(e.to_string()))?;
 
-        if row.is_some() {
-            self.record_hit();
-        } else {
-            self.record_miss();
-        }
+        self.record_access(source, row.is_some());
 
         Ok(row.map(Self::row_to_entry))
     }
 
     /// Get a cache entry by checksum (tracks hit/miss statistics)
     pub async fn get_by_checksum(&self, checksum: &str) -> CogniaResult<Option<CacheEntry>> {
+        self.get_by_checksum_for_source(checksum, "download").await
+    }
+
+    /// Get a cache entry by checksum, attributing the hit/miss to `source`
+    pub async fn get_by_checksum_for_source(
+        &self,
+        checksum: &str,
+        source: &str,
+    ) -> CogniaResult<Option<CacheEntry>> {
         let row: Option<CacheEntryRow> =
             sqlx::query_as("SELECT * FROM cache_entries WHERE checksum = ?")
                 .bind(checksum)
@@ -357,11 +598,7 @@ pub async fn get_by_checksum(&self, checksum: &str) -> CogniaResult<Option<Cache
                 .await
                 .map_err(|e| CogniaError::Internal(e.to_string()))?;
 
-        if row.is_some() {
-            self.record_hit();
-        } else {
-            self.record_miss();
-        }
+        self.record_access(source, row.is_some());
 
         Ok(row.map(Self::row_to_entry))
     }
@@ -904,6 +1141,8 @@ pub async fn get_db_info(&self) -> CogniaResult<DatabaseInfo> {
             "cache_entries",
             "cache_access_stats",
             "cache_size_snapshots",
+            "cache_usage_samples",
+            "cache_references",
         ] {
             let query = format!("SELECT COUNT(*) FROM {}", table);
             let count: i64 = sqlx::query_scalar(&query)
@@ -1013,6 +1252,164 @@ pub async fn prune_old_snapshots(&self, max_age_days: u32) -> CogniaResult<usize
         Ok(result.rows_affected() as usize)
     }
 
+    // ==================== Usage Samples ====================
+
+    /// Persist a cache size monitor sample. Like size snapshots, samples are
+    /// taken at most once per hour and kept for 90 days.
+    /// `external_size` is `None` when external caches were not scanned.
+    pub async fn record_usage_sample(
+        &self,
+        internal_size: u64,
+        external_size: Option<u64>,
+        total_size: u64,
+    ) -> CogniaResult<()> {
+        let last_ts: Option<String> = sqlx::query_scalar(
+            "SELECT timestamp FROM cache_usage_samples ORDER BY id DESC LIMIT 1",
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| CogniaError::Internal(format!("Failed to query last sample: {}", e)))?;
+
+        if let Some(ts) = last_ts {
+            if let Ok(dt) = ts.parse::<DateTime<Utc>>() {
+                if Utc::now() - dt < ChronoDuration::hours(1) {
+                    return Ok(());
+                }
+            }
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO cache_usage_samples (timestamp, internal_size, external_size, total_size)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(Utc::now().to_rfc3339())
+        .bind(internal_size as i64)
+        .bind(external_size.map(|size| size as i64))
+        .bind(total_size as i64)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| CogniaError::Internal(format!("Failed to record usage sample: {}", e)))?;
+
+        let cutoff = Utc::now() - ChronoDuration::days(90);
+        sqlx::query("DELETE FROM cache_usage_samples WHERE timestamp < ?")
+            .bind(cutoff.to_rfc3339())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| CogniaError::Internal(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Get usage samples for the last N days, oldest first
+    pub async fn get_usage_samples(&self, days: u32) -> CogniaResult<Vec<CacheUsageSample>> {
+        let cutoff = Utc::now() - ChronoDuration::days(days as i64);
+
+        #[derive(FromRow)]
+        struct SampleRow {
+            timestamp: String,
+            internal_size: i64,
+            external_size: Option<i64>,
+            total_size: i64,
+        }
+
+        let rows: Vec<SampleRow> = sqlx::query_as(
+            "SELECT timestamp, internal_size, external_size, total_size FROM cache_usage_samples WHERE timestamp >= ? ORDER BY timestamp ASC",
+        )
+        .bind(cutoff.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| CogniaError::Internal(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| CacheUsageSample {
+                timestamp: r.timestamp,
+                internal_size: r.internal_size as u64,
+                external_size: r.external_size.map(|size| size as u64),
+                total_size: r.total_size as u64,
+            })
+            .collect())
+    }
+
+    // ==================== Environment References ====================
+
+    /// Record that `env_type@version` was installed from the artifact with `checksum`
+    pub async fn add_reference(
+        &self,
+        checksum: &str,
+        env_type: &str,
+        version: &str,
+    ) -> CogniaResult<()> {
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO cache_references (checksum, env_type, version, created_at)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(checksum)
+        .bind(env_type)
+        .bind(version)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| CogniaError::Internal(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Drop every reference held by `env_type@version`
+    pub async fn remove_references(&self, env_type: &str, version: &str) -> CogniaResult<usize> {
+        let result = sqlx::query("DELETE FROM cache_references WHERE env_type = ? AND version = ?")
+            .bind(env_type)
+            .bind(version)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| CogniaError::Internal(e.to_string()))?;
+
+        Ok(result.rows_affected() as usize)
+    }
+
+    /// List all environment references
+    pub async fn list_references(&self) -> CogniaResult<Vec<CacheReference>> {
+        #[derive(FromRow)]
+        struct ReferenceRow {
+            checksum: String,
+            env_type: String,
+            version: String,
+            created_at: String,
+        }
+
+        let rows: Vec<ReferenceRow> = sqlx::query_as(
+            "SELECT checksum, env_type, version, created_at FROM cache_references ORDER BY env_type, version",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| CogniaError::Internal(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| CacheReference {
+                checksum: row.checksum,
+                env_type: row.env_type,
+                version: row.version,
+                created_at: row.created_at.parse().unwrap_or_else(|_| Utc::now()),
+            })
+            .collect())
+    }
+
+    /// Checksums referenced by at least one installed environment version
+    pub async fn referenced_checksums(&self) -> CogniaResult<HashSet<String>> {
+        let rows: Vec<String> =
+            sqlx::query_scalar("SELECT DISTINCT checksum FROM cache_references")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| CogniaError::Internal(e.to_string()))?;
+
+        Ok(rows.into_iter().collect())
+    }
+
     // Helper: Convert entry type to string
     fn entry_type_to_str(entry_type: CacheEntryType) -> &'static str {
         match entry_type {
@@ -1035,6 +1432,22 @@ fn str_to_entry_type(s: &str) -> CacheEntryType {
     }
 }
 
+/// An installed environment version that was built from a cached artifact
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheReference {
+    pub checksum: String,
+    pub env_type: String,
+    pub version: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl CacheReference {
+    /// Display label such as `node@20.11.0`
+    pub fn label(&self) -> String {
+        format!("{}@{}", self.env_type, self.version)
+    }
+}
+
 /// Result of a database integrity check
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IntegrityCheckResult {
@@ -1067,6 +1480,25 @@ pub struct CacheSizeSnapshot {
     pub metadata_count: usize,
 }
 
+/// Cache size monitor sample
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheUsageSample {
+    pub timestamp: String,
+    pub internal_size: u64,
+    /// `None` when the sample did not scan external caches
+    pub external_size: Option<u64>,
+    pub total_size: u64,
+}
+
+impl Drop for SqliteCacheDb {
+    /// Most handles live for a single command, so write what they counted
+    fn drop(&mut self) {
+        let deltas = self.take_source_deltas();
+        self.spawn_source_flush(deltas);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1467,6 +1899,68 @@ async fn test_sqlite_cache_persist_stats() {
         }
     }
 
+    #[test]
+    fn test_access_source_for_key() {
+        assert_eq!(
+            SqliteCacheDb::access_source_for_key("metadata:plugin:marketplace:index"),
+            "plugin"
+        );
+        assert_eq!(
+            SqliteCacheDb::access_source_for_key("metadata:solo"),
+            "metadata"
+        );
+        assert_eq!(
+            SqliteCacheDb::access_source_for_key("checksum:abc"),
+            "download"
+        );
+        assert_eq!(SqliteCacheDb::access_source_for_key("npm:react"), "npm");
+        assert_eq!(SqliteCacheDb::access_source_for_key("plain"), "other");
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_cache_access_stats_by_source() {
+        let dir = tempdir().unwrap();
+
+        {
+            let db = SqliteCacheDb::open(dir.path()).await.unwrap();
+            db.insert(CacheEntry::new(
+                "checksum:c1",
+                dir.path().join("f"),
+                100,
+                "c1",
+                CacheEntryType::Download,
+            ))
+            .await
+            .unwrap();
+
+            let _ = db.get_for_source("checksum:c1", "npm").await.unwrap(); // hit
+            let _ = db.get_for_source("checksum:c2", "npm").await.unwrap(); // miss
+            let _ = db.get_by_checksum_for_source("c1", "pip").await.unwrap(); // hit
+            let _ = db.get("metadata:cargo:serde").await.unwrap(); // miss
+            db.persist_stats().await.unwrap();
+        }
+
+        // Persisted counters survive the handle
+        let db = SqliteCacheDb::open(dir.path()).await.unwrap();
+        let by_source = db.access_stats_by_source().await.unwrap();
+        let find = |source: &str| {
+            by_source
+                .iter()
+                .find(|s| s.source == source)
+                .cloned()
+                .unwrap()
+        };
+        assert_eq!(by_source.len(), 3);
+        assert_eq!(by_source[0].source, "npm");
+        assert_eq!((find("npm").hits, find("npm").misses), (1, 1));
+        assert!((find("npm").hit_rate - 0.5).abs() < 0.01);
+        assert_eq!((find("pip").hits, find("pip").misses), (1, 0));
+        assert_eq!((find("cargo").hits, find("cargo").misses), (0, 1));
+
+        db.reset_access_stats().await.unwrap();
+        assert!(db.access_stats_by_source().await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_sqlite_cache_list_filtered() {
         let dir = tempdir().unwrap();
@@ -1691,6 +2185,34 @@ async fn test_record_and_get_snapshots() {
         assert!(!snapshots[0].internal_size_human.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_record_usage_samples_is_throttled_and_pruned() {
+        let dir = tempdir().unwrap();
+        let db = SqliteCacheDb::open(dir.path()).await.unwrap();
+
+        let old_ts = (Utc::now() - ChronoDuration::days(120)).to_rfc3339();
+        sqlx::query(
+            "INSERT INTO cache_usage_samples (timestamp, internal_size, external_size, total_size) VALUES (?, ?, ?, ?)",
+        )
+        .bind(&old_ts)
+        .bind(100i64)
+        .bind(Option::<i64>::None)
+        .bind(100i64)
+        .execute(&db.pool)
+        .await
+        .unwrap();
+
+        db.record_usage_sample(1000, Some(500), 1500).await.unwrap();
+        // Within the hour of the previous sample, so skipped
+        db.record_usage_sample(2000, None, 2000).await.unwrap();
+
+        let samples = db.get_usage_samples(365).await.unwrap();
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].internal_size, 1000);
+        assert_eq!(samples[0].external_size, Some(500));
+        assert_eq!(samples[0].total_size, 1500);
+    }
+
     #[tokio::test]
     async fn test_prune_old_snapshots() {
         let dir = tempdir().unwrap();
@@ -1760,6 +2282,29 @@ async fn test_sqlite_evict_to_size() {
         assert!(stats.total_size <= 2000);
     }
 
+    #[tokio::test]
+    async fn test_sqlite_cache_references() {
+        let dir = tempdir().unwrap();
+        let db = SqliteCacheDb::open(dir.path()).await.unwrap();
+
+        db.add_reference("abc", "node", "20.11.0").await.unwrap();
+        db.add_reference("abc", "node", "20.11.0").await.unwrap();
+        db.add_reference("abc", "node", "18.19.0").await.unwrap();
+        db.add_reference("def", "python", "3.12.1").await.unwrap();
+
+        let refs = db.list_references().await.unwrap();
+        assert_eq!(refs.len(), 3);
+        assert_eq!(refs[0].label(), "node@18.19.0");
+
+        let referenced = db.referenced_checksums().await.unwrap();
+        assert_eq!(referenced.len(), 2);
+        assert!(referenced.contains("abc"));
+
+        assert_eq!(db.remove_references("python", "3.12.1").await.unwrap(), 1);
+        assert_eq!(db.remove_references("python", "3.12.1").await.unwrap(), 0);
+        assert!(!db.referenced_checksums().await.unwrap().contains("def"));
+    }
+
     #[tokio::test]
     async fn test_sqlite_evict_under_limit_noop() {
         let dir = tempdir().unwrap();
@@ -0,0 +1,501 @@
+//! Integrity verification for the download cache
+//!
+//! Files are hashed in parallel on blocking threads with a bounded number of
+//! workers. Files that verified clean are remembered together with their size
+//! and mtime, so an interrupted or repeated run skips everything that has not
+//! changed since. Corrupted files are moved into a quarantine directory under
+//! the cache root, where they can be inspected, restored or purged.
+
+use super::CacheEntry;
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::fs;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Directory under the cache root holding quarantined files
+pub const QUARANTINE_DIR: &str = "quarantine";
+
+const QUARANTINE_INDEX_FILE: &str = "index.json";
+const VERIFY_STATE_FILE: &str = "cache-verify-state.json";
+
+/// Default number of files hashed at once
+pub fn default_concurrency() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4)
+}
+
+/// Verification progress, emitted as `cache-verify-progress`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyProgress {
+    pub entries_done: usize,
+    pub entries_total: usize,
+    /// Unchanged files skipped since an earlier run verified them
+    pub entries_skipped: usize,
+    pub bytes_hashed: u64,
+    pub bytes_total: u64,
+}
+
+impl VerifyProgress {
+    pub fn percent(&self) -> f64 {
+        if self.entries_total == 0 {
+            100.0
+        } else {
+            self.entries_done as f64 * 100.0 / self.entries_total as f64
+        }
+    }
+}
+
+/// One cached file to check against the checksum the index expects
+#[derive(Debug, Clone)]
+pub struct VerifyTask {
+    /// Index keys pointing at this file (checksum and per-source keys)
+    pub entry_keys: Vec<String>,
+    pub path: PathBuf,
+    pub expected_checksum: String,
+    pub size: u64,
+    /// Modification time in milliseconds since the epoch
+    pub modified_ms: i64,
+}
+
+/// Download cache entries sorted by what verification has to do with them
+#[derive(Debug, Default)]
+pub struct VerifyPlan {
+    /// Files that need hashing
+    pub tasks: Vec<VerifyTask>,
+    pub missing: Vec<VerifyTask>,
+    /// Files whose size differs from the index, with their size on disk
+    pub size_mismatches: Vec<(VerifyTask, u64)>,
+    /// Files skipped because they are unchanged since they last verified clean
+    pub unchanged: usize,
+}
+
+fn modified_ms(metadata: &std::fs::Metadata) -> i64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// Group index entries by file and check each file's size and mtime
+pub async fn plan_verification(entries: Vec<CacheEntry>, state: &VerifyState) -> VerifyPlan {
+    let mut groups: Vec<VerifyTask> = Vec::new();
+    let mut by_path: HashMap<PathBuf, usize> = HashMap::new();
+    for entry in entries {
+        match by_path.get(&entry.file_path) {
+            Some(&index) => groups[index].entry_keys.push(entry.key),
+            None => {
+                by_path.insert(entry.file_path.clone(), groups.len());
+                groups.push(VerifyTask {
+                    entry_keys: vec![entry.key],
+                    path: entry.file_path,
+                    expected_checksum: entry.checksum,
+                    size: entry.size,
+                    modified_ms: 0,
+                });
+            }
+        }
+    }
+
+    let mut plan = VerifyPlan::default();
+    for mut task in groups {
+        let Ok(metadata) = tokio::fs::metadata(&task.path).await else {
+            plan.missing.push(task);
+            continue;
+        };
+        task.modified_ms = modified_ms(&metadata);
+        if metadata.len() != task.size {
+            plan.size_mismatches.push((task, metadata.len()));
+        } else if state.is_unchanged(&task) {
+            plan.unchanged += 1;
+        } else {
+            plan.tasks.push(task);
+        }
+    }
+    plan
+}
+
+fn sha256_file(path: &Path) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Hash tasks with at most `concurrency` files in flight, yielding results in
+/// completion order. No new file is started once `is_cancelled` returns true.
+pub fn hash_files<'a>(
+    tasks: Vec<VerifyTask>,
+    concurrency: usize,
+    is_cancelled: impl Fn() -> bool + 'a,
+) -> impl Stream<Item = (VerifyTask, Result<String, String>)> + 'a {
+    stream::iter(tasks)
+        .take_while(move |_| futures::future::ready(!is_cancelled()))
+        .map(|task| async move {
+            let path = task.path.clone();
+            let result = match tokio::task::spawn_blocking(move || sha256_file(&path)).await {
+                Ok(hashed) => hashed.map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            (task, result)
+        })
+        .buffer_unordered(concurrency.max(1))
+}
+
+/// Fingerprint of a file that verified clean
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifiedFile {
+    pub checksum: String,
+    pub size: u64,
+    pub modified_ms: i64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct VerifyStateIndex {
+    version: u32,
+    files: HashMap<String, VerifiedFile>,
+}
+
+/// Files verified by earlier runs, keyed by path
+pub struct VerifyState {
+    path: PathBuf,
+    index: VerifyStateIndex,
+}
+
+impl VerifyState {
+    pub async fn open(cache_dir: &Path) -> CogniaResult<Self> {
+        let path = cache_dir.join(VERIFY_STATE_FILE);
+        let index = if fs::exists(&path).await {
+            let content = fs::read_file_string(&path).await?;
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            VerifyStateIndex::default()
+        };
+        Ok(Self { path, index })
+    }
+
+    pub fn is_unchanged(&self, task: &VerifyTask) -> bool {
+        self.index
+            .files
+            .get(&task.path.display().to_string())
+            .is_some_and(|file| {
+                file.checksum == task.expected_checksum
+                    && file.size == task.size
+                    && file.modified_ms == task.modified_ms
+            })
+    }
+
+    pub fn mark_verified(&mut self, task: &VerifyTask) {
+        self.index.files.insert(
+            task.path.display().to_string(),
+            VerifiedFile {
+                checksum: task.expected_checksum.clone(),
+                size: task.size,
+                modified_ms: task.modified_ms,
+            },
+        );
+    }
+
+    pub fn forget(&mut self, path: &Path) {
+        self.index.files.remove(&path.display().to_string());
+    }
+
+    /// Drop records of files that are no longer in the cache index
+    pub fn retain_paths(&mut self, paths: &HashSet<String>) {
+        self.index.files.retain(|path, _| paths.contains(path));
+    }
+
+    pub async fn save(&mut self) -> CogniaResult<()> {
+        self.index.version = 1;
+        let content =
+            serde_json::to_string(&self.index).map_err(|e| CogniaError::Internal(e.to_string()))?;
+        fs::write_file_atomic(&self.path, content.as_bytes()).await?;
+        Ok(())
+    }
+}
+
+/// A corrupted cache file moved out of the download cache
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantineEntry {
+    pub id: String,
+    pub original_path: String,
+    pub quarantined_path: String,
+    /// Index keys removed when the file was quarantined, re-added on restore
+    pub entry_keys: Vec<String>,
+    pub expected_checksum: String,
+    /// `None` when the file was quarantined for its size alone
+    pub actual_checksum: Option<String>,
+    pub size: u64,
+    /// `checksum_mismatch` or `size_mismatch`
+    pub reason: String,
+    pub quarantined_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct QuarantineIndex {
+    version: u32,
+    entries: Vec<QuarantineEntry>,
+}
+
+/// Quarantine directory and its index
+pub struct Quarantine {
+    dir: PathBuf,
+    index: QuarantineIndex,
+}
+
+impl Quarantine {
+    pub async fn open(cache_dir: &Path) -> CogniaResult<Self> {
+        let dir = cache_dir.join(QUARANTINE_DIR);
+        let index_path = dir.join(QUARANTINE_INDEX_FILE);
+        let index = if fs::exists(&index_path).await {
+            let content = fs::read_file_string(&index_path).await?;
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            QuarantineIndex::default()
+        };
+        Ok(Self { dir, index })
+    }
+
+    /// Quarantined files, most recent first
+    pub fn list(&self) -> &[QuarantineEntry] {
+        &self.index.entries
+    }
+
+    /// Move `task`'s file into quarantine
+    pub async fn add(
+        &mut self,
+        task: &VerifyTask,
+        actual_checksum: Option<String>,
+        size: u64,
+        reason: &str,
+    ) -> CogniaResult<QuarantineEntry> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let file_name = task
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "entry".to_string());
+        let quarantined_path = self.dir.join(format!("{}-{}", id, file_name));
+        fs::move_file(&task.path, &quarantined_path).await?;
+
+        let entry = QuarantineEntry {
+            id,
+            original_path: task.path.display().to_string(),
+            quarantined_path: quarantined_path.display().to_string(),
+            entry_keys: task.entry_keys.clone(),
+            expected_checksum: task.expected_checksum.clone(),
+            actual_checksum,
+            size,
+            reason: reason.to_string(),
+            quarantined_at: Utc::now(),
+        };
+        self.index.entries.insert(0, entry.clone());
+        self.save().await?;
+        Ok(entry)
+    }
+
+    /// Move a quarantined file back to where it was. The caller re-indexes it.
+    pub async fn restore(&mut self, id: &str) -> CogniaResult<QuarantineEntry> {
+        let position = self
+            .index
+            .entries
+            .iter()
+            .position(|entry| entry.id == id)
+            .ok_or_else(|| CogniaError::Internal(format!("Quarantine entry not found: {}", id)))?;
+        let entry = &self.index.entries[position];
+        if fs::exists(&entry.original_path).await {
+            return Err(CogniaError::Internal(format!(
+                "Cannot restore {}: a file already exists at that path",
+                entry.original_path
+            )));
+        }
+        fs::move_file(&entry.quarantined_path, &entry.original_path).await?;
+
+        let entry = self.index.entries.remove(position);
+        self.save().await?;
+        Ok(entry)
+    }
+
+    /// Delete quarantined files, all of them when `ids` is `None`
+    pub async fn purge(&mut self, ids: Option<&[String]>) -> CogniaResult<Vec<QuarantineEntry>> {
+        let (purged, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.index.entries)
+            .into_iter()
+            .partition(|entry| ids.map_or(true, |ids| ids.contains(&entry.id)));
+        self.index.entries = kept;
+
+        for entry in &purged {
+            if fs::exists(&entry.quarantined_path).await {
+                fs::remove_file(&entry.quarantined_path).await?;
+            }
+        }
+        self.save().await?;
+        Ok(purged)
+    }
+
+    async fn save(&mut self) -> CogniaResult<()> {
+        self.index.version = 1;
+        let content = serde_json::to_string_pretty(&self.index)
+            .map_err(|e| CogniaError::Internal(e.to_string()))?;
+        fs::create_dir_all(&self.dir).await?;
+        fs::write_file_atomic(self.dir.join(QUARANTINE_INDEX_FILE), content.as_bytes()).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CacheEntryType;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::tempdir;
+
+    fn sha256_hex(content: &[u8]) -> String {
+        hex::encode(Sha256::digest(content))
+    }
+
+    async fn cached_file(dir: &Path, name: &str, content: &[u8], checksum: &str) -> CacheEntry {
+        let path = dir.join(name);
+        tokio::fs::write(&path, content).await.unwrap();
+        CacheEntry::new(
+            format!("checksum:{}", checksum),
+            &path,
+            content.len() as u64,
+            checksum,
+            CacheEntryType::Download,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_plan_groups_keys_and_sorts_entries() {
+        let dir = tempdir().unwrap();
+        let good = cached_file(dir.path(), "good", b"hello", &sha256_hex(b"hello")).await;
+        let mut source_key = good.clone();
+        source_key.key = "checksum:x:source:y".to_string();
+        let mut resized = cached_file(dir.path(), "resized", b"abc", "00").await;
+        resized.size = 10;
+        let missing = CacheEntry::new(
+            "checksum:gone",
+            dir.path().join("gone"),
+            1,
+            "gone",
+            CacheEntryType::Download,
+        );
+
+        let state = VerifyState::open(dir.path()).await.unwrap();
+        let plan = plan_verification(vec![good, source_key, resized, missing], &state).await;
+
+        assert_eq!(plan.tasks.len(), 1);
+        assert_eq!(plan.tasks[0].entry_keys.len(), 2);
+        assert_eq!(plan.missing.len(), 1);
+        assert_eq!(plan.size_mismatches.len(), 1);
+        assert_eq!(plan.size_mismatches[0].1, 3);
+        assert_eq!(plan.unchanged, 0);
+    }
+
+    #[tokio::test]
+    async fn test_verified_files_are_skipped_until_changed() {
+        let dir = tempdir().unwrap();
+        let entry = cached_file(dir.path(), "file", b"hello", &sha256_hex(b"hello")).await;
+
+        let mut state = VerifyState::open(dir.path()).await.unwrap();
+        let plan = plan_verification(vec![entry.clone()], &state).await;
+        state.mark_verified(&plan.tasks[0]);
+        state.save().await.unwrap();
+
+        let state = VerifyState::open(dir.path()).await.unwrap();
+        let plan = plan_verification(vec![entry.clone()], &state).await;
+        assert_eq!(plan.unchanged, 1);
+        assert!(plan.tasks.is_empty());
+
+        let mut changed = state;
+        changed.forget(&entry.file_path);
+        let plan = plan_verification(vec![entry], &changed).await;
+        assert_eq!(plan.tasks.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_hash_files_reports_mismatches() {
+        let dir = tempdir().unwrap();
+        let good = cached_file(dir.path(), "good", b"hello", &sha256_hex(b"hello")).await;
+        let bad = cached_file(dir.path(), "bad", b"tampered", &sha256_hex(b"original")).await;
+        let state = VerifyState::open(dir.path()).await.unwrap();
+        let plan = plan_verification(vec![good, bad], &state).await;
+
+        let results: Vec<_> = hash_files(plan.tasks, 2, || false).collect().await;
+        assert_eq!(results.len(), 2);
+        for (task, result) in results {
+            let matches = result.unwrap() == task.expected_checksum;
+            assert_eq!(matches, task.path.ends_with("good"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hash_files_stops_starting_work_when_cancelled() {
+        let dir = tempdir().unwrap();
+        let mut entries = Vec::new();
+        for i in 0..5 {
+            let name = format!("file{}", i);
+            entries.push(cached_file(dir.path(), &name, name.as_bytes(), "00").await);
+        }
+        let state = VerifyState::open(dir.path()).await.unwrap();
+        let plan = plan_verification(entries, &state).await;
+
+        let started = AtomicUsize::new(0);
+        let results: Vec<_> = hash_files(plan.tasks, 1, || {
+            started.fetch_add(1, Ordering::SeqCst) >= 2
+        })
+        .collect()
+        .await;
+        assert_eq!(results.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_quarantine_restore_and_purge() {
+        let dir = tempdir().unwrap();
+        let first = cached_file(dir.path(), "first", b"one", "aa").await;
+        let second = cached_file(dir.path(), "second", b"two", "bb").await;
+        let state = VerifyState::open(dir.path()).await.unwrap();
+        let plan = plan_verification(vec![first.clone(), second.clone()], &state).await;
+
+        let mut quarantine = Quarantine::open(dir.path()).await.unwrap();
+        let a = quarantine
+            .add(&plan.tasks[0], Some("ff".into()), 3, "checksum_mismatch")
+            .await
+            .unwrap();
+        let b = quarantine
+            .add(&plan.tasks[1], Some("ee".into()), 3, "checksum_mismatch")
+            .await
+            .unwrap();
+        assert!(!first.file_path.exists());
+        assert!(Path::new(&a.quarantined_path).exists());
+
+        let mut quarantine = Quarantine::open(dir.path()).await.unwrap();
+        assert_eq!(quarantine.list().len(), 2);
+        assert_eq!(quarantine.list()[0].id, b.id);
+
+        let restored = quarantine.restore(&a.id).await.unwrap();
+        assert_eq!(restored.entry_keys, vec![first.key.clone()]);
+        assert!(first.file_path.exists());
+        assert!(quarantine.restore(&a.id).await.is_err());
+
+        let purged = quarantine.purge(None).await.unwrap();
+        assert_eq!(purged.len(), 1);
+        assert!(!Path::new(&b.quarantined_path).exists());
+        assert!(quarantine.list().is_empty());
+    }
+}
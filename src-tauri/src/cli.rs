@@ -127,6 +127,20 @@ pub fn has_cli_subcommand() -> bool {
         .any(|arg| SUBCOMMANDS.contains(&arg.as_str()))
 }
 
+/// Launched by the OS to open a `cognia://` link
+pub fn is_deep_link_launch() -> bool {
+    std::env::args()
+        .skip(1)
+        .any(|arg| crate::core::deep_link::is_deep_link(&arg))
+}
+
+/// Launched by the OS scheduler for a headless update check
+pub fn is_background_update_check() -> bool {
+    std::env::args()
+        .skip(1)
+        .any(|arg| arg == crate::platform::scheduler::BACKGROUND_UPDATE_CHECK_FLAG)
+}
+
 struct CliContext {
     app: tauri::AppHandle,
     settings: Settings,
@@ -148,6 +162,11 @@ fn from_app(app: &tauri::AppHandle) -> Self {
 }
 
 pub fn handle_cli(app: &tauri::AppHandle) -> Option<i32> {
+    // A cognia:// link is not a CLI argument; the GUI picks it up
+    if is_deep_link_launch() && !has_cli_subcommand() {
+        return None;
+    }
+
     let matches = match app.cli().matches() {
         Ok(m) => m,
         Err(e) => {
@@ -554,12 +573,15 @@ async fn create_cli_download_manager(settings: &Settings) -> DownloadManager {
         partials_dir: settings.get_cache_dir().join("partials"),
         auto_start: true,
         progress_interval_ms: 100,
+        ..Default::default()
     };
 
     let client = crate::platform::proxy::build_client(settings);
     let mut manager = DownloadManager::new(config, client);
     manager.enable_persistence(&settings.get_cache_dir());
-    let _ = manager.load_persisted_tasks().await;
+    let _ = manager
+        .load_persisted_tasks(settings.general.download_auto_resume)
+        .await;
     manager
 }
 
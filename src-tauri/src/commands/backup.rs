@@ -2,16 +2,25 @@
 use crate::commands::config::SharedSettings;
 use crate::commands::custom_detection::SharedCustomDetectionManager;
 use crate::commands::terminal::SharedTerminalProfileManager;
+use crate::commands::workspace::SharedWorkspaceManager;
 use crate::core::backup::{
     self, BackupCleanupResult, BackupContentType, BackupDeleteResult, BackupExportResult,
     BackupImportResult, BackupInfo, BackupManifest, BackupOperationIssue, BackupOperationStatus,
     BackupResult, BackupValidationResult, RestoreResult,
 };
+use crate::core::custom_detection::CustomDetectionManager;
+use crate::core::full_backup::{
+    self, FullBackupInspection, FullBackupOptions, FullBackupProgressCallback, FullBackupResult,
+    FullRestoreResult, StateComponent, StateLocations,
+};
 use crate::core::profiles::SharedProfileManager;
+use crate::core::terminal::TerminalProfileManager;
+use crate::core::workspace::WorkspaceManager;
 use chrono::Utc;
 use std::collections::HashMap;
 use std::path::PathBuf;
-use tauri::State;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager, State};
 
 fn parse_content_types(contents: &[String]) -> (Vec<BackupContentType>, Vec<String>) {
     if contents.is_empty() {
@@ -29,6 +38,19 @@ fn parse_content_types(contents: &[String]) -> (Vec<BackupContentType>, Vec<Stri
     (parsed, invalid)
 }
 
+/// Empty input selects every component in the archive
+fn parse_state_components(components: &[String]) -> (Vec<StateComponent>, Vec<String>) {
+    let mut parsed = Vec::new();
+    let mut invalid = Vec::new();
+    for item in components {
+        match StateComponent::from_str(item) {
+            Some(component) => parsed.push(component),
+            None => invalid.push(item.clone()),
+        }
+    }
+    (parsed, invalid)
+}
+
 fn invalid_content_issues(invalid_contents: &[String]) -> Vec<BackupOperationIssue> {
     invalid_contents
         .iter()
@@ -197,6 +219,170 @@ pub async fn backup_cleanup(
     Ok(backup::cleanup_old_backups_with_result(&s, max_count, max_age_days).await)
 }
 
+async fn state_locations(app: &AppHandle, settings: &SharedSettings) -> StateLocations {
+    let app_config_dir = app.path().app_config_dir().unwrap_or_default();
+    StateLocations::new(&settings.read().await, app_config_dir)
+}
+
+fn full_backup_progress_emitter(app: &AppHandle) -> FullBackupProgressCallback {
+    let app = app.clone();
+    Arc::new(move |progress| {
+        let _ = app.emit("backup-full-progress", progress);
+    })
+}
+
+/// Reload the restored components that running subsystems hold in memory.
+/// Returns an issue for each one that could not be reloaded.
+async fn reload_restored_state(
+    app: &AppHandle,
+    restored: &[StateComponent],
+    locations: &StateLocations,
+) -> Vec<BackupOperationIssue> {
+    let mut issues = Vec::new();
+    for component in restored {
+        let reloaded = match component {
+            StateComponent::Config => reload_settings(app).await,
+            StateComponent::TerminalProfiles => {
+                match TerminalProfileManager::new(&locations.root_dir).await {
+                    Ok(manager) => {
+                        *app.state::<SharedTerminalProfileManager>().write().await = manager;
+                        Ok(())
+                    }
+                    Err(e) => Err(e.to_string()),
+                }
+            }
+            StateComponent::CustomRules => {
+                let mut manager = CustomDetectionManager::new(&locations.app_config_dir);
+                match manager.load().await {
+                    Ok(()) => {
+                        *app.state::<SharedCustomDetectionManager>().write().await = manager;
+                        Ok(())
+                    }
+                    Err(e) => Err(e.to_string()),
+                }
+            }
+            StateComponent::Workspaces => {
+                let mut manager = WorkspaceManager::new(&locations.app_config_dir);
+                match manager.load().await {
+                    Ok(()) => {
+                        *app.state::<crate::commands::workspace::SharedWorkspaceManager>()
+                            .write()
+                            .await = manager;
+                        Ok(())
+                    }
+                    Err(e) => Err(e.to_string()),
+                }
+            }
+            _ => Ok(()),
+        };
+        if let Err(message) = reloaded {
+            issues.push(BackupOperationIssue {
+                code: "restore_reload_failed".to_string(),
+                message,
+                content_type: Some(component.as_str().to_string()),
+            });
+        }
+    }
+    issues
+}
+
+async fn reload_settings(app: &AppHandle) -> Result<(), String> {
+    let mut next = crate::config::Settings::load()
+        .await
+        .map_err(|e| e.to_string())?;
+    let vault = app.state::<crate::SharedSecretVault>();
+    crate::secrets::migrate_provider_secrets(
+        &mut next,
+        &*vault.read().await,
+        crate::platform::secrets::global(),
+    )
+    .await?;
+    crate::commands::config::replace_settings(
+        app,
+        app.state::<SharedSettings>().inner(),
+        app.state::<crate::SharedRegistry>().inner(),
+        next,
+    )
+    .await
+}
+
+/// Archive the whole Cognia state directory into a single zip. Emits
+/// `backup-full-progress`.
+#[tauri::command]
+pub async fn backup_full_create(
+    options: FullBackupOptions,
+    app: AppHandle,
+    settings: State<'_, SharedSettings>,
+) -> Result<FullBackupResult, String> {
+    let locations = state_locations(&app, settings.inner()).await;
+    Ok(full_backup::create_full_backup_with_result(
+        &locations,
+        &options,
+        Some(full_backup_progress_emitter(&app)),
+    )
+    .await)
+}
+
+/// Read a full backup's manifest and check it can be restored
+#[tauri::command]
+pub async fn backup_inspect(path: String) -> Result<FullBackupInspection, String> {
+    Ok(full_backup::inspect_full_backup(&PathBuf::from(&path)).await)
+}
+
+/// Restore the selected components (all when empty) from a full backup,
+/// then reload the ones that can be applied without a restart. Emits
+/// `backup-full-progress`.
+#[tauri::command]
+pub async fn backup_full_restore(
+    path: String,
+    components: Vec<String>,
+    app: AppHandle,
+    settings: State<'_, SharedSettings>,
+) -> Result<FullRestoreResult, String> {
+    let (components, invalid_components) = parse_state_components(&components);
+    if !invalid_components.is_empty() {
+        return Ok(FullRestoreResult {
+            success: false,
+            status: BackupOperationStatus::Failed,
+            reason_code: Some("invalid_content_types".to_string()),
+            issues: invalid_content_issues(&invalid_components),
+            restored: vec![],
+            config_migrated_from: None,
+            restart_required: false,
+            duration_ms: 0,
+            error: Some(format!(
+                "Unsupported backup components: {}",
+                invalid_components.join(", ")
+            )),
+        });
+    }
+
+    let locations = state_locations(&app, settings.inner()).await;
+    let mut result = full_backup::restore_full_backup_with_result(
+        &PathBuf::from(&path),
+        &components,
+        &locations,
+        Some(full_backup_progress_emitter(&app)),
+    )
+    .await;
+
+    if result.success {
+        let issues = reload_restored_state(&app, &result.restored, &locations).await;
+        if !issues.is_empty() {
+            result.success = false;
+            result.status = BackupOperationStatus::Partial;
+            result.reason_code = Some("restore_reload_failed".to_string());
+            result.error = Some(format!(
+                "{} restored component(s) could not be reloaded; restart to apply them",
+                issues.len()
+            ));
+            result.restart_required = true;
+            result.issues.extend(issues);
+        }
+    }
+    Ok(result)
+}
+
 #[tauri::command]
 pub async fn db_integrity_check(
     settings: State<'_, SharedSettings>,
@@ -253,6 +439,24 @@ fn test_parse_content_types_collects_invalid_values() {
         assert_eq!(invalid, vec!["invalid_item".to_string()]);
     }
 
+    #[test]
+    fn test_parse_state_components_collects_invalid_values() {
+        let (parsed, invalid) = parse_state_components(&[
+            "terminal_profiles".to_string(),
+            "custom_rules".to_string(),
+            "cache_database".to_string(),
+        ]);
+        assert_eq!(
+            parsed,
+            vec![
+                StateComponent::TerminalProfiles,
+                StateComponent::CustomRules
+            ]
+        );
+        assert_eq!(invalid, vec!["cache_database".to_string()]);
+        assert!(parse_state_components(&[]).0.is_empty());
+    }
+
     #[test]
     fn test_invalid_content_issues_include_content_type() {
         let issues = invalid_content_issues(&["bad-type".to_string()]);
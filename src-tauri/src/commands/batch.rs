@@ -1,7 +1,8 @@
 use crate::config::Settings;
+use crate::core::operation_queue::{JobKind, SharedOperationQueue};
 use crate::core::{
-    BatchInstallRequest, BatchManager, BatchProgress, BatchResult, HistoryAction, HistoryManager,
-    HistoryQuery, PackageSpec,
+    BatchInstallRequest, BatchManager, BatchProgress, BatchResult, BatchUpdateOptions,
+    CancellationToken, HistoryAction, HistoryManager, HistoryQuery, PackageSpec,
 };
 use crate::platform::current_platform;
 use crate::provider::node_base::{normalize_node_package_name, normalize_node_provider_id};
@@ -13,7 +14,10 @@
     SUPPORT_STATUS_UNSUPPORTED,
 };
 use crate::provider::ProviderRegistry;
-use crate::resolver::{Dependency, Package, Resolver, Version, VersionConstraint};
+use crate::resolver::{
+    DecisionLogEntry, Dependency, Package, ResolutionFailureExplanation, Resolver, Version,
+    VersionConstraint,
+};
 use futures::future::{join_all, BoxFuture};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -81,10 +85,13 @@ pub async fn batch_install(
     app_handle: AppHandle,
     registry: State<'_, SharedRegistry>,
     settings: State<'_, SharedSettings>,
+    jobs: State<'_, SharedOperationQueue>,
 ) -> Result<BatchResult, String> {
     let settings_ref = settings.inner().clone();
     let settings = settings.read().await.clone();
-    let manager = BatchManager::new(registry.inner().clone(), settings);
+    let cancel_token = CancellationToken::new();
+    let manager = BatchManager::new(registry.inner().clone(), settings)
+        .with_cancel_token(cancel_token.clone());
 
     let request = BatchInstallRequest {
         packages,
@@ -94,12 +101,27 @@ pub async fn batch_install(
         global: global.unwrap_or(true),
     };
 
+    let job = jobs
+        .inner()
+        .start(
+            JobKind::BatchInstall,
+            format!("Install {} package(s)", request.packages.len()),
+            serde_json::to_value(&request).unwrap_or_default(),
+            Some(cancel_token.flag()),
+        )
+        .await;
+
     let result = manager
         .batch_install(request, |progress| {
             emit_batch_progress(&app_handle, &progress);
+            if let Some(percent) = batch_progress_percent(&progress) {
+                job.progress(Some(percent), None);
+            }
         })
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| e.to_string());
+    job.finish(&result).await;
+    let result = result?;
 
     // Invalidate package caches after successful batch install
     crate::commands::package::invalidate_package_caches(&settings_ref).await;
@@ -107,6 +129,16 @@ pub async fn batch_install(
     Ok(result)
 }
 
+/// Share of items processed, for the job's progress
+fn batch_progress_percent(progress: &BatchProgress) -> Option<f64> {
+    match progress {
+        BatchProgress::ItemCompleted { current, total, .. } if *total > 0 => {
+            Some(*current as f64 / *total as f64 * 100.0)
+        }
+        _ => None,
+    }
+}
+
 /// Batch uninstall packages
 #[tauri::command]
 pub async fn batch_uninstall(
@@ -134,9 +166,13 @@ pub async fn batch_uninstall(
 }
 
 /// Batch update packages
+///
+/// With `verify_after_update`, each updated package is verified and rolled back
+/// on failure; rolled-back packages are pinned to the restored version.
 #[tauri::command]
 pub async fn batch_update(
     packages: Option<Vec<String>>,
+    verify_after_update: Option<bool>,
     app_handle: AppHandle,
     registry: State<'_, SharedRegistry>,
     settings: State<'_, SharedSettings>,
@@ -144,14 +180,32 @@ pub async fn batch_update(
     let settings_ref = settings.inner().clone();
     let settings = settings.read().await.clone();
     let manager = BatchManager::new(registry.inner().clone(), settings);
+    let options = BatchUpdateOptions {
+        verify_after_update: verify_after_update.unwrap_or(false),
+    };
 
-    let result = manager
-        .batch_update(packages, |progress| {
+    let mut result = manager
+        .batch_update_with_options(packages, options, |progress| {
             emit_batch_progress(&app_handle, &progress);
         })
         .await
         .map_err(|e| e.to_string())?;
 
+    if !result.rolled_back.is_empty() {
+        let mut settings_guard = settings_ref.write().await;
+        for item in &result.rolled_back {
+            settings_guard.provider_settings.pinned_packages.insert(
+                format!("{}:{}", item.provider, item.name),
+                Some(item.to_version.clone()),
+            );
+        }
+        if settings_guard.save().await.is_ok() {
+            for item in &mut result.rolled_back {
+                item.pinned = true;
+            }
+        }
+    }
+
     // Invalidate package caches after successful batch update
     crate::commands::package::invalidate_package_caches(&settings_ref).await;
 
@@ -208,16 +262,8 @@ pub struct RequiredVersion {
     pub constraint: String,
 }
 
-#[tauri::command]
-pub async fn resolve_dependencies(
-    packages: Vec<String>,
-    registry: State<'_, SharedRegistry>,
-    settings: State<'_, SharedSettings>,
-) -> Result<ResolutionResult, String> {
-    let reg = registry.read().await;
-
-    let specs: Vec<PackageSpec> = packages.iter().map(|p| PackageSpec::parse(p)).collect();
-    let deps: Vec<Dependency> = specs
+fn root_dependencies(specs: &[PackageSpec]) -> Vec<Dependency> {
+    specs
         .iter()
         .map(|spec| Dependency {
             name: spec.name.clone(),
@@ -230,59 +276,29 @@ pub async fn resolve_dependencies(
                 })
                 .unwrap_or(VersionConstraint::Any),
         })
-        .collect();
+        .collect()
+}
 
-    // Create resolver and add available packages
-    let mut resolver = Resolver::new();
+/// Available versions and dependency metadata gathered from providers for a
+/// set of root package specs.
+struct ResolutionUniverse {
+    resolver: Resolver,
+    /// Which provider provides each package
+    package_providers: std::collections::HashMap<String, String>,
+    /// Dependency lookup failures, kept visible in result payloads
+    dependency_lookup_failures: std::collections::HashMap<String, String>,
+}
 
-    // Track which provider provides each package
+async fn collect_resolution_universe(
+    specs: &[PackageSpec],
+    reg: &ProviderRegistry,
+) -> ResolutionUniverse {
+    let mut resolver = Resolver::new();
     let mut package_providers: std::collections::HashMap<String, String> =
         std::collections::HashMap::new();
-    // Record dependency lookup failures so they remain visible in result payloads.
     let mut dependency_lookup_failures: std::collections::HashMap<String, String> =
         std::collections::HashMap::new();
 
-    // Collect installed packages for comparison (prefer cached data)
-    let mut installed_packages: std::collections::HashMap<String, String> =
-        std::collections::HashMap::new();
-
-    // Try MetadataCache first (written by package_list)
-    let mut used_cache = false;
-    {
-        let cache_dir = settings.read().await.get_cache_dir();
-        if let Ok(mut cache) = crate::cache::MetadataCache::open_with_ttl(&cache_dir, 60).await {
-            if let Ok(Some(cached)) = cache
-                .get::<Vec<crate::provider::InstalledPackage>>("pkg:installed:all")
-                .await
-            {
-                if !cached.is_stale {
-                    for pkg in cached.data {
-                        installed_packages.insert(package_lookup_key(&pkg.name), pkg.version);
-                    }
-                    used_cache = true;
-                }
-            }
-        }
-    }
-
-    // Fallback: live scan
-    if !used_cache {
-        for provider_id in reg.list() {
-            if let Some(provider) = reg.get(provider_id) {
-                if provider.is_available().await {
-                    if let Ok(installed) = provider
-                        .list_installed(crate::provider::InstalledFilter::default())
-                        .await
-                    {
-                        for pkg in installed {
-                            installed_packages.insert(package_lookup_key(&pkg.name), pkg.version);
-                        }
-                    }
-                }
-            }
-        }
-    }
-
     let provider_ids: Vec<String> = reg
         .list()
         .iter()
@@ -361,6 +377,71 @@ pub async fn resolve_dependencies(
         }
     }
 
+    ResolutionUniverse {
+        resolver,
+        package_providers,
+        dependency_lookup_failures,
+    }
+}
+
+#[tauri::command]
+pub async fn resolve_dependencies(
+    packages: Vec<String>,
+    registry: State<'_, SharedRegistry>,
+    settings: State<'_, SharedSettings>,
+) -> Result<ResolutionResult, String> {
+    let reg = registry.read().await;
+
+    let specs: Vec<PackageSpec> = packages.iter().map(|p| PackageSpec::parse(p)).collect();
+    let deps = root_dependencies(&specs);
+
+    // Collect installed packages for comparison (prefer cached data)
+    let mut installed_packages: std::collections::HashMap<String, String> =
+        std::collections::HashMap::new();
+
+    // Try MetadataCache first (written by package_list)
+    let mut used_cache = false;
+    {
+        let cache_dir = settings.read().await.get_cache_dir();
+        if let Ok(mut cache) = crate::cache::MetadataCache::open_with_ttl(&cache_dir, 60).await {
+            if let Ok(Some(cached)) = cache
+                .get::<Vec<crate::provider::InstalledPackage>>("pkg:installed:all")
+                .await
+            {
+                if !cached.is_stale {
+                    for pkg in cached.data {
+                        installed_packages.insert(package_lookup_key(&pkg.name), pkg.version);
+                    }
+                    used_cache = true;
+                }
+            }
+        }
+    }
+
+    // Fallback: live scan
+    if !used_cache {
+        for provider_id in reg.list() {
+            if let Some(provider) = reg.get(provider_id) {
+                if provider.is_available().await {
+                    if let Ok(installed) = provider
+                        .list_installed(crate::provider::InstalledFilter::default())
+                        .await
+                    {
+                        for pkg in installed {
+                            installed_packages.insert(package_lookup_key(&pkg.name), pkg.version);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let ResolutionUniverse {
+        resolver,
+        package_providers,
+        dependency_lookup_failures,
+    } = collect_resolution_universe(&specs, &reg).await;
+
     // Resolve
     match resolver.resolve(&deps) {
         Ok(resolution) => {
@@ -478,6 +559,62 @@ fn build_node<'a>(
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolutionExplainResult {
+    pub success: bool,
+    pub packages: Vec<ResolvedPackage>,
+    pub explanation: Option<ResolutionFailureExplanation>,
+    pub decision_log: Option<Vec<DecisionLogEntry>>,
+    pub lookup_failures: Vec<ConflictInfo>,
+}
+
+/// Resolve dependencies with backtracking; on failure return the derivation of
+/// the clashing constraints and suggested relaxations instead of a flat error.
+#[tauri::command]
+pub async fn resolve_dependencies_explain(
+    packages: Vec<String>,
+    include_decision_log: Option<bool>,
+    registry: State<'_, SharedRegistry>,
+) -> Result<ResolutionExplainResult, String> {
+    let reg = registry.read().await;
+
+    let specs: Vec<PackageSpec> = packages.iter().map(|p| PackageSpec::parse(p)).collect();
+    let deps = root_dependencies(&specs);
+    let ResolutionUniverse {
+        resolver,
+        package_providers,
+        dependency_lookup_failures,
+    } = collect_resolution_universe(&specs, &reg).await;
+    drop(reg);
+
+    let explained = resolver
+        .resolve_explained(&deps, include_decision_log.unwrap_or(false))
+        .map_err(|e| e.to_string())?;
+
+    let mut resolved_packages: Vec<ResolvedPackage> = explained
+        .resolution
+        .iter()
+        .flat_map(|resolution| resolution.iter())
+        .map(|(name, version)| ResolvedPackage {
+            name: name.clone(),
+            version: version.to_string(),
+            provider: package_providers
+                .get(&package_lookup_key(name))
+                .cloned()
+                .unwrap_or_default(),
+        })
+        .collect();
+    resolved_packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(ResolutionExplainResult {
+        success: explained.success,
+        packages: resolved_packages,
+        explanation: explained.explanation,
+        decision_log: explained.decision_log,
+        lookup_failures: build_dependency_lookup_conflicts(&dependency_lookup_failures),
+    })
+}
+
 /// Progress events for update checking
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateCheckProgress {
@@ -1231,6 +1368,8 @@ pub struct InstallHistoryEntry {
     pub provider: String,
     pub success: bool,
     pub error_message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor_plugin_id: Option<String>,
 }
 
 #[tauri::command]
@@ -1318,6 +1457,7 @@ fn to_install_history_entries(
             provider: e.provider,
             success: e.success,
             error_message: e.error_message,
+            actor_plugin_id: e.actor_plugin_id,
         })
         .collect()
 }
@@ -1326,6 +1466,24 @@ fn to_install_history_entries(
 mod tests {
     use super::*;
 
+    #[test]
+    fn batch_progress_percent_tracks_completed_items() {
+        let completed = BatchProgress::ItemCompleted {
+            package: "ripgrep".into(),
+            success: true,
+            current: 1,
+            total: 4,
+        };
+        assert_eq!(batch_progress_percent(&completed), Some(25.0));
+
+        let installing = BatchProgress::Installing {
+            package: "ripgrep".into(),
+            current: 1,
+            total: 4,
+        };
+        assert_eq!(batch_progress_percent(&installing), None);
+    }
+
     #[test]
     fn classify_update_type_returns_semver_segments() {
         assert_eq!(classify_update_type("1.2.3", "2.0.0"), "major");
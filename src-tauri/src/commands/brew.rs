@@ -1,6 +1,7 @@
 use crate::error::{CogniaError, CogniaResult};
 use crate::platform::process::{self, ProcessOptions};
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 // ── Helpers ──────────────────────────────────────────────────────────────────
@@ -80,6 +81,46 @@ pub struct BrewConfigEntry {
     pub value: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BrewCask {
+    pub token: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub homepage: Option<String>,
+    pub tap: Option<String>,
+    /// Latest version known to the cask definition
+    pub version: String,
+    pub installed_version: Option<String>,
+    pub outdated: bool,
+    pub auto_updates: bool,
+    pub deprecated: bool,
+    pub apps: Vec<BrewCaskAppBundle>,
+    /// An installed app bundle reports a different version than brew installed,
+    /// i.e. the app updated itself or was replaced outside of brew
+    pub manually_updated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BrewCaskAppBundle {
+    /// Bundle name as installed, e.g. `Visual Studio Code.app`
+    pub name: String,
+    pub path: String,
+    pub exists: bool,
+    pub bundle_version: Option<String>,
+    /// `None` when either side has no comparable version (e.g. cask version `latest`)
+    pub version_matches: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BrewCaskOutdated {
+    pub token: String,
+    pub installed_versions: Vec<String>,
+    pub current_version: String,
+}
+
 // ── Tap Management ───────────────────────────────────────────────────────────
 
 #[tauri::command]
@@ -320,6 +361,261 @@ pub async fn brew_get_config() -> CogniaResult<BrewConfigInfo> {
     })
 }
 
+// ── Casks ────────────────────────────────────────────────────────────────────
+
+const APP_DIRS: &[&str] = &["/Applications", "~/Applications"];
+
+/// Extract the app bundle names a cask installs from its `artifacts` list.
+/// Entries look like `{"app": ["Foo.app"]}` or `{"app": ["Foo.app", {"target": "Bar.app"}]}`.
+fn cask_app_artifacts(cask: &serde_json::Value) -> Vec<String> {
+    let Some(artifacts) = cask["artifacts"].as_array() else {
+        return Vec::new();
+    };
+    artifacts
+        .iter()
+        .filter_map(|artifact| artifact["app"].as_array())
+        .filter_map(|app| {
+            let source = app.first()?.as_str()?;
+            let target = app
+                .iter()
+                .skip(1)
+                .find_map(|option| option["target"].as_str())
+                .unwrap_or(source);
+            Some(target.to_string())
+        })
+        .collect()
+}
+
+fn parse_cask_json(cask: &serde_json::Value) -> Option<BrewCask> {
+    let token = cask["token"].as_str()?.to_string();
+    Some(BrewCask {
+        name: cask["name"]
+            .as_array()
+            .and_then(|names| names.first())
+            .and_then(|name| name.as_str())
+            .map(str::to_string),
+        description: cask["desc"].as_str().map(str::to_string),
+        homepage: cask["homepage"].as_str().map(str::to_string),
+        tap: cask["tap"].as_str().map(str::to_string),
+        version: cask["version"].as_str().unwrap_or("").to_string(),
+        installed_version: cask["installed"].as_str().map(str::to_string),
+        outdated: cask["outdated"].as_bool().unwrap_or(false),
+        auto_updates: cask["auto_updates"].as_bool().unwrap_or(false),
+        deprecated: cask["deprecated"].as_bool().unwrap_or(false),
+        apps: cask_app_artifacts(cask)
+            .into_iter()
+            .map(|name| BrewCaskAppBundle {
+                path: name.clone(),
+                name,
+                exists: false,
+                bundle_version: None,
+                version_matches: None,
+            })
+            .collect(),
+        manually_updated: false,
+        token,
+    })
+}
+
+/// Parse `brew info --json=v2 --cask` output.
+fn parse_cask_info_output(output: &str) -> Vec<BrewCask> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(output) else {
+        return Vec::new();
+    };
+    json["casks"]
+        .as_array()
+        .map(|casks| casks.iter().filter_map(parse_cask_json).collect())
+        .unwrap_or_default()
+}
+
+/// Parse `brew outdated --cask --json=v2` output.
+fn parse_cask_outdated_output(output: &str) -> Vec<BrewCaskOutdated> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(output) else {
+        return Vec::new();
+    };
+    json["casks"]
+        .as_array()
+        .map(|casks| {
+            casks
+                .iter()
+                .filter_map(|cask| {
+                    Some(BrewCaskOutdated {
+                        token: cask["name"].as_str()?.to_string(),
+                        installed_versions: cask["installed_versions"]
+                            .as_array()
+                            .map(|versions| {
+                                versions
+                                    .iter()
+                                    .filter_map(|v| v.as_str().map(str::to_string))
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
+                        current_version: cask["current_version"].as_str()?.to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Read a string value from an XML property list.
+fn plist_string_value(content: &str, key: &str) -> Option<String> {
+    let marker = format!("<key>{}</key>", key);
+    let rest = &content[content.find(&marker)? + marker.len()..];
+    let rest = rest.trim_start().strip_prefix("<string>")?;
+    let value = rest[..rest.find("</string>")?].trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+async fn read_bundle_version(bundle: &Path) -> Option<String> {
+    let info_plist = bundle.join("Contents").join("Info.plist");
+    if let Ok(content) = tokio::fs::read_to_string(&info_plist).await {
+        if let Some(version) = plist_string_value(&content, "CFBundleShortVersionString") {
+            return Some(version);
+        }
+    }
+    // Binary plists are not valid UTF-8; let plutil decode them.
+    let path = info_plist.to_string_lossy();
+    let out = process::execute(
+        "plutil",
+        &[
+            "-extract",
+            "CFBundleShortVersionString",
+            "raw",
+            "-o",
+            "-",
+            path.as_ref(),
+        ],
+        None,
+    )
+    .await
+    .ok()?;
+    let version = out.stdout.trim();
+    (out.success && !version.is_empty()).then(|| version.to_string())
+}
+
+/// Compare a bundle's short version with the version brew installed. Cask
+/// versions may carry a build suffix after a comma (`1.85.2,8b3775030e`).
+fn bundle_version_matches(cask_version: &str, bundle_version: &str) -> Option<bool> {
+    let cask_version = cask_version.split(',').next().unwrap_or("").trim();
+    if cask_version.is_empty() || cask_version == "latest" || bundle_version.trim().is_empty() {
+        return None;
+    }
+    Some(cask_version == bundle_version.trim())
+}
+
+fn resolve_app_dir(dir: &str) -> Option<PathBuf> {
+    match dir.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().map(|home| home.join(rest)),
+        None => Some(PathBuf::from(dir)),
+    }
+}
+
+/// Locate each app artifact on disk and compare its bundle version against
+/// the installed cask version.
+async fn correlate_app_bundles(cask: &mut BrewCask) {
+    let installed_version = cask
+        .installed_version
+        .clone()
+        .unwrap_or_else(|| cask.version.clone());
+
+    for app in &mut cask.apps {
+        let candidates: Vec<PathBuf> = if Path::new(&app.name).is_absolute() {
+            vec![PathBuf::from(&app.name)]
+        } else {
+            APP_DIRS
+                .iter()
+                .filter_map(|dir| resolve_app_dir(dir))
+                .map(|dir| dir.join(&app.name))
+                .collect()
+        };
+        let Some(bundle) = candidates.iter().find(|path| path.exists()) else {
+            if let Some(first) = candidates.first() {
+                app.path = first.to_string_lossy().to_string();
+            }
+            continue;
+        };
+
+        app.exists = true;
+        app.path = bundle.to_string_lossy().to_string();
+        app.bundle_version = read_bundle_version(bundle).await;
+        app.version_matches = app
+            .bundle_version
+            .as_deref()
+            .and_then(|version| bundle_version_matches(&installed_version, version));
+    }
+
+    cask.manually_updated = cask
+        .apps
+        .iter()
+        .any(|app| app.version_matches == Some(false));
+}
+
+async fn load_casks(args: &[&str]) -> CogniaResult<Vec<BrewCask>> {
+    let out = run_brew(args).await?;
+    let mut casks = parse_cask_info_output(&out);
+    for cask in &mut casks {
+        if cask.installed_version.is_some() {
+            correlate_app_bundles(cask).await;
+        }
+    }
+    Ok(casks)
+}
+
+#[tauri::command]
+pub async fn brew_cask_list() -> CogniaResult<Vec<BrewCask>> {
+    load_casks(&["info", "--json=v2", "--cask", "--installed"]).await
+}
+
+#[tauri::command]
+pub async fn brew_cask_info(name: String) -> CogniaResult<BrewCask> {
+    load_casks(&["info", "--json=v2", "--cask", &name])
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| CogniaError::Provider(format!("Cask not found: {}", name)))
+}
+
+/// List outdated casks. `greedy` includes casks that update themselves
+/// (`auto_updates true`) or use `version :latest`.
+#[tauri::command]
+pub async fn brew_cask_outdated(greedy: Option<bool>) -> CogniaResult<Vec<BrewCaskOutdated>> {
+    let mut args = vec!["outdated", "--cask", "--json=v2"];
+    if greedy.unwrap_or(false) {
+        args.push("--greedy");
+    }
+    let out = run_brew(&args).await?;
+    Ok(parse_cask_outdated_output(&out))
+}
+
+#[tauri::command]
+pub async fn brew_cask_install(name: String) -> CogniaResult<String> {
+    run_brew(&["install", "--cask", &name]).await
+}
+
+#[tauri::command]
+pub async fn brew_cask_uninstall(name: String, zap: Option<bool>) -> CogniaResult<String> {
+    let mut args = vec!["uninstall", "--cask"];
+    if zap.unwrap_or(false) {
+        args.push("--zap");
+    }
+    args.push(&name);
+    run_brew(&args).await
+}
+
+/// Upgrade one cask, or every outdated cask when `name` is omitted.
+#[tauri::command]
+pub async fn brew_cask_upgrade(name: Option<String>, greedy: Option<bool>) -> CogniaResult<String> {
+    let mut args = vec!["upgrade", "--cask"];
+    if greedy.unwrap_or(false) {
+        args.push("--greedy");
+    }
+    if let Some(name) = name.as_deref() {
+        args.push(name);
+    }
+    run_brew(&args).await
+}
+
 // ── Analytics ────────────────────────────────────────────────────────────────
 
 #[tauri::command]
@@ -355,6 +651,60 @@ fn test_parse_services_output() {
         assert_eq!(services[1].user, None);
     }
 
+    #[test]
+    fn test_parse_cask_info_output() {
+        let output = r#"{"formulae":[],"casks":[{"token":"visual-studio-code","name":["Microsoft Visual Studio Code"],"desc":"Open-source code editor","homepage":"https://code.visualstudio.com/","tap":"homebrew/cask","version":"1.85.2,8b3775030e","installed":"1.85.1,0ee08df0cf","outdated":true,"auto_updates":true,"deprecated":false,"artifacts":[{"app":["Visual Studio Code.app"]},{"binary":["$APPBUNDLE/Contents/Resources/app/bin/code"]}]},{"token":"iterm2","name":["iTerm2"],"version":"3.4.23","installed":null,"artifacts":[{"app":["iTerm.app",{"target":"iTerm2.app"}]},{"zap":[{"trash":["~/Library/Preferences/com.googlecode.iterm2.plist"]}]}]}]}"#;
+
+        let casks = parse_cask_info_output(output);
+        assert_eq!(casks.len(), 2);
+        assert_eq!(casks[0].token, "visual-studio-code");
+        assert_eq!(
+            casks[0].name.as_deref(),
+            Some("Microsoft Visual Studio Code")
+        );
+        assert_eq!(
+            casks[0].installed_version.as_deref(),
+            Some("1.85.1,0ee08df0cf")
+        );
+        assert!(casks[0].outdated);
+        assert!(casks[0].auto_updates);
+        assert_eq!(casks[0].apps.len(), 1);
+        assert_eq!(casks[0].apps[0].name, "Visual Studio Code.app");
+        assert_eq!(casks[1].installed_version, None);
+        assert_eq!(casks[1].apps[0].name, "iTerm2.app");
+        assert!(parse_cask_info_output("not json").is_empty());
+    }
+
+    #[test]
+    fn test_parse_cask_outdated_output() {
+        let output = r#"{"formulae":[],"casks":[{"name":"firefox","installed_versions":["121.0"],"current_version":"122.0"}]}"#;
+        let outdated = parse_cask_outdated_output(output);
+        assert_eq!(outdated.len(), 1);
+        assert_eq!(outdated[0].token, "firefox");
+        assert_eq!(outdated[0].installed_versions, vec!["121.0"]);
+        assert_eq!(outdated[0].current_version, "122.0");
+    }
+
+    #[test]
+    fn test_plist_string_value_and_bundle_version_matching() {
+        let plist = "<dict>\n\t<key>CFBundleName</key>\n\t<string>Code</string>\n\t<key>CFBundleShortVersionString</key>\n\t<string>1.86.0</string>\n</dict>";
+        assert_eq!(
+            plist_string_value(plist, "CFBundleShortVersionString").as_deref(),
+            Some("1.86.0")
+        );
+        assert_eq!(plist_string_value(plist, "CFBundleVersion"), None);
+
+        assert_eq!(
+            bundle_version_matches("1.85.2,8b3775030e", "1.85.2"),
+            Some(true)
+        );
+        assert_eq!(
+            bundle_version_matches("1.85.2,8b3775030e", "1.86.0"),
+            Some(false)
+        );
+        assert_eq!(bundle_version_matches("latest", "1.86.0"), None);
+    }
+
     #[test]
     fn test_parse_services_empty() {
         let output = "Name  Status  User  File\n";
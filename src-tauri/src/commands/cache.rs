@@ -1,17 +1,23 @@
 use crate::cache::{
-    external, migration, CacheAccessStats, CacheEntry, CacheEntryType, CacheSizeSnapshot,
+    analytics::{self, CleanupAnalytics, CleanupAnalyticsPeriod},
+    external, external_preview, migration,
+    verify::{self, VerifyTask},
+    CacheAccessStats, CacheEntry, CacheEntryType, CacheReference, CacheSizeSnapshot,
     CleanupHistory, CleanupRecord, CleanupRecordBuilder, CombinedCacheStats, DownloadCache,
     DownloadHistory, DownloadResumer, ExternalCacheCleanResult, ExternalCacheInfo, MetadataCache,
-    MigrationMode, MigrationResult, MigrationValidation,
+    MigrationMode, MigrationOptions, MigrationProgress, MigrationResult, MigrationValidation,
+    Quarantine, QuarantineEntry, VerifyProgress, VerifyState,
 };
 use crate::config::{settings::CustomCacheEntry, Settings};
+use crate::core::operation_queue::{JobHandle, JobKind, SharedOperationQueue};
 use crate::platform::{disk, disk::format_size, fs, process::ProcessOptions, PlatformPaths};
 use chrono::{DateTime, TimeZone, Utc};
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter, State};
 use tokio::sync::RwLock;
 
@@ -529,6 +535,10 @@ pub async fn cache_clean(
 
     let clean_type_str = clean_type.as_deref().unwrap_or("all");
     let max_age = Duration::from_secs(max_age_days as u64 * 86400);
+    let references = download_cache
+        .references_by_checksum()
+        .await
+        .map_err(|e| e.to_string())?;
 
     let mut resumer = DownloadResumer::new(&cache_dir.join("downloads"))
         .await
@@ -656,6 +666,11 @@ pub struct CacheVerificationResult {
     pub size_mismatches: usize,
     pub is_healthy: bool,
     pub details: Vec<CacheIssue>,
+    /// Corrupted download files moved into the quarantine directory
+    pub quarantined_entries: usize,
+    /// Download files skipped because they are unchanged since they last verified clean
+    pub skipped_unchanged: usize,
+    pub bytes_hashed: u64,
 }
 
 #[derive(Serialize)]
@@ -665,10 +680,51 @@ pub struct CacheIssue {
     pub description: String,
 }
 
+/// Minimum delay between two `cache-verify-progress` events
+const VERIFY_PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Verified files recorded between two saves of the verification state
+const VERIFY_STATE_SAVE_EVERY: usize = 100;
+
+/// Verify cache integrity
+///
+/// Download files are hashed in parallel (`concurrency` workers, one per CPU
+/// by default) and corrupted ones are moved into quarantine. Progress is
+/// emitted as `cache-verify-progress` events. The run is tracked as a job and
+/// can be cancelled with `jobs_cancel`; running it again skips files that
+/// verified clean before and have not changed since.
 #[tauri::command]
 pub async fn cache_verify(
     scope: Option<String>,
+    concurrency: Option<usize>,
+    app: AppHandle,
+    settings: State<'_, SharedSettings>,
+    jobs: State<'_, SharedOperationQueue>,
+) -> Result<CacheVerificationResult, String> {
+    let job = jobs
+        .inner()
+        .start(
+            JobKind::CacheVerify,
+            "Verify cache integrity",
+            serde_json::json!({
+                "scope": scope,
+                "concurrency": concurrency,
+            }),
+            None,
+        )
+        .await;
+
+    let result = run_cache_verify(scope, concurrency, &app, settings, &job).await;
+    job.finish(&result).await;
+    result
+}
+
+async fn run_cache_verify(
+    scope: Option<String>,
+    concurrency: Option<usize>,
+    app: &AppHandle,
     settings: State<'_, SharedSettings>,
+    job: &JobHandle,
 ) -> Result<CacheVerificationResult, String> {
     let s = settings.read().await;
     let cache_dir = s.get_cache_dir();
@@ -676,7 +732,7 @@ pub async fn cache_verify(
     drop(s);
     let scope = CacheCommandScope::parse(scope.as_deref())?;
 
-    let download_cache = DownloadCache::open(&cache_dir)
+    let mut download_cache = DownloadCache::open(&cache_dir)
         .await
         .map_err(|e| e.to_string())?;
     let metadata_cache = MetadataCache::open_with_ttl(&cache_dir, metadata_cache_ttl)
@@ -692,59 +748,38 @@ pub async fn cache_verify(
         .await
         .map_err(|e| e.to_string())?;
 
-    let mut details: Vec<CacheIssue> = Vec::new();
-    let mut valid_entries = 0usize;
-    let mut missing_files = 0usize;
-    let mut corrupted_files = 0usize;
-    let mut size_mismatches = 0usize;
+    let mut result = CacheVerificationResult {
+        valid_entries: 0,
+        missing_files: 0,
+        corrupted_files: 0,
+        size_mismatches: 0,
+        is_healthy: false,
+        details: Vec::new(),
+        quarantined_entries: 0,
+        skipped_unchanged: 0,
+        bytes_hashed: 0,
+    };
 
     if scope.includes_download() {
-        for entry in dl_entries {
-            if !fs::exists(&entry.file_path).await {
-                missing_files += 1;
-                details.push(CacheIssue {
-                    entry_key: entry.key,
-                    issue_type: "missing".to_string(),
-                    description: "File not found on disk".to_string(),
-                });
-                continue;
-            }
-
-            let actual_size = fs::file_size(&entry.file_path).await.unwrap_or(entry.size);
-            if actual_size != entry.size {
-                size_mismatches += 1;
-                details.push(CacheIssue {
-                    entry_key: entry.key.clone(),
-                    issue_type: "size_mismatch".to_string(),
-                    description: format!(
-                        "Expected {} bytes, got {} bytes",
-                        entry.size, actual_size
-                    ),
-                });
-                continue;
-            }
-
-            let actual_checksum = fs::calculate_sha256(&entry.file_path)
-                .await
-                .map_err(|e| e.to_string())?;
-            if actual_checksum != entry.checksum {
-                corrupted_files += 1;
-                details.push(CacheIssue {
-                    entry_key: entry.key,
-                    issue_type: "checksum_mismatch".to_string(),
-                    description: "File content has been corrupted".to_string(),
-                });
-                continue;
-            }
-
-            valid_entries += 1;
-        }
+        verify_download_entries(
+            app,
+            &cache_dir,
+            metadata_cache_ttl,
+            &mut download_cache,
+            dl_entries,
+            concurrency.unwrap_or_else(verify::default_concurrency),
+            job,
+            &mut result,
+        )
+        .await?;
     }
 
+    let details = &mut result.details;
+
     if scope.includes_metadata() {
         for entry in md_entries {
             if !fs::exists(&entry.file_path).await {
-                missing_files += 1;
+                result.missing_files += 1;
                 details.push(CacheIssue {
                     entry_key: format!("metadata:{}", entry.key),
                     issue_type: "missing".to_string(),
@@ -755,7 +790,7 @@ pub async fn cache_verify(
 
             let actual_size = fs::file_size(&entry.file_path).await.unwrap_or(entry.size);
             if actual_size != entry.size {
-                size_mismatches += 1;
+                result.size_mismatches += 1;
                 details.push(CacheIssue {
                     entry_key: format!("metadata:{}", entry.key),
                     issue_type: "size_mismatch".to_string(),
@@ -767,7 +802,7 @@ pub async fn cache_verify(
                 continue;
             }
 
-            valid_entries += 1;
+            result.valid_entries += 1;
         }
     }
 
@@ -775,9 +810,9 @@ pub async fn cache_verify(
         let default_downloads = collect_default_downloads_candidates(&cache_dir).await;
         for candidate in default_downloads.candidates {
             if fs::exists(&candidate.path).await {
-                valid_entries += 1;
+                result.valid_entries += 1;
             } else {
-                missing_files += 1;
+                result.missing_files += 1;
                 details.push(CacheIssue {
                     entry_key: candidate.path.display().to_string(),
                     issue_type: "missing".to_string(),
@@ -787,7 +822,7 @@ pub async fn cache_verify(
         }
         for skipped in default_downloads.skipped {
             if skipped.reason == "file_missing" {
-                missing_files += 1;
+                result.missing_files += 1;
             }
             details.push(CacheIssue {
                 entry_key: skipped.path,
@@ -797,13 +832,296 @@ pub async fn cache_verify(
         }
     }
 
-    Ok(CacheVerificationResult {
-        valid_entries,
-        missing_files,
-        corrupted_files,
-        size_mismatches,
-        is_healthy: missing_files == 0 && corrupted_files == 0 && size_mismatches == 0,
-        details,
+    result.is_healthy =
+        result.missing_files == 0 && result.corrupted_files == 0 && result.size_mismatches == 0;
+    Ok(result)
+}
+
+/// Hash download cache files in parallel and quarantine the corrupted ones
+#[allow(clippy::too_many_arguments)]
+async fn verify_download_entries(
+    app: &AppHandle,
+    cache_dir: &Path,
+    metadata_cache_ttl: i64,
+    download_cache: &mut DownloadCache,
+    entries: Vec<CacheEntry>,
+    concurrency: usize,
+    job: &JobHandle,
+    result: &mut CacheVerificationResult,
+) -> Result<(), String> {
+    let mut state = VerifyState::open(cache_dir)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut quarantine = Quarantine::open(cache_dir)
+        .await
+        .map_err(|e| e.to_string())?;
+    state.retain_paths(
+        &entries
+            .iter()
+            .map(|entry| entry.file_path.display().to_string())
+            .collect(),
+    );
+
+    let plan = verify::plan_verification(entries, &state).await;
+    let mut record = CleanupRecordBuilder::new("quarantine", false);
+    let mut quarantined_bytes = 0u64;
+
+    for task in &plan.missing {
+        state.forget(&task.path);
+        result.missing_files += 1;
+        result.details.push(CacheIssue {
+            entry_key: task.entry_keys[0].clone(),
+            issue_type: "missing".to_string(),
+            description: "File not found on disk".to_string(),
+        });
+    }
+
+    for (task, actual_size) in &plan.size_mismatches {
+        state.forget(&task.path);
+        result.size_mismatches += 1;
+        let outcome = quarantine_download(
+            &mut quarantine,
+            download_cache,
+            &mut record,
+            task,
+            None,
+            *actual_size,
+            "size_mismatch",
+        )
+        .await;
+        if outcome.is_ok() {
+            result.quarantined_entries += 1;
+            quarantined_bytes += actual_size;
+        }
+        result.details.push(CacheIssue {
+            entry_key: task.entry_keys[0].clone(),
+            issue_type: "size_mismatch".to_string(),
+            description: quarantine_description(
+                format!("Expected {} bytes, got {} bytes", task.size, actual_size),
+                &outcome,
+            ),
+        });
+    }
+
+    result.valid_entries += plan.unchanged;
+    result.skipped_unchanged = plan.unchanged;
+
+    let mut progress = VerifyProgress {
+        entries_done: plan.unchanged,
+        entries_total: plan.unchanged + plan.tasks.len(),
+        entries_skipped: plan.unchanged,
+        bytes_hashed: 0,
+        bytes_total: plan.tasks.iter().map(|task| task.size).sum(),
+    };
+    let report_progress = |progress: &VerifyProgress| {
+        let _ = app.emit("cache-verify-progress", progress);
+        job.progress(
+            Some(progress.percent()),
+            Some(format!(
+                "Verified {}/{} files",
+                progress.entries_done, progress.entries_total
+            )),
+        );
+    };
+    report_progress(&progress);
+
+    let mut last_report = Instant::now();
+    let mut unsaved = 0usize;
+    let mut hashed = std::pin::pin!(verify::hash_files(plan.tasks, concurrency, || {
+        job.is_cancelled()
+    }));
+    while let Some((task, actual)) = hashed.next().await {
+        progress.entries_done += 1;
+        progress.bytes_hashed += task.size;
+
+        match actual {
+            Ok(actual) if actual == task.expected_checksum => {
+                result.valid_entries += 1;
+                state.mark_verified(&task);
+                unsaved += 1;
+            }
+            Ok(actual) => {
+                state.forget(&task.path);
+                result.corrupted_files += 1;
+                let outcome = quarantine_download(
+                    &mut quarantine,
+                    download_cache,
+                    &mut record,
+                    &task,
+                    Some(actual),
+                    task.size,
+                    "checksum_mismatch",
+                )
+                .await;
+                if outcome.is_ok() {
+                    result.quarantined_entries += 1;
+                    quarantined_bytes += task.size;
+                }
+                result.details.push(CacheIssue {
+                    entry_key: task.entry_keys[0].clone(),
+                    issue_type: "checksum_mismatch".to_string(),
+                    description: quarantine_description(
+                        "File content has been corrupted".to_string(),
+                        &outcome,
+                    ),
+                });
+            }
+            Err(e) => {
+                state.forget(&task.path);
+                result.corrupted_files += 1;
+                result.details.push(CacheIssue {
+                    entry_key: task.entry_keys[0].clone(),
+                    issue_type: "read_error".to_string(),
+                    description: format!("Failed to read file: {}", e),
+                });
+            }
+        }
+
+        if unsaved >= VERIFY_STATE_SAVE_EVERY {
+            state.save().await.map_err(|e| e.to_string())?;
+            unsaved = 0;
+        }
+        if last_report.elapsed() >= VERIFY_PROGRESS_INTERVAL {
+            report_progress(&progress);
+            last_report = Instant::now();
+        }
+    }
+
+    state.save().await.map_err(|e| e.to_string())?;
+    result.bytes_hashed = progress.bytes_hashed;
+    report_progress(&progress);
+
+    let quarantined = record.build();
+    if quarantined.file_count > 0 {
+        finalize_internal_cache_mutation(
+            app,
+            cache_dir,
+            metadata_cache_ttl,
+            "quarantine",
+            CacheCommandScope::Download,
+            quarantined_bytes,
+            Some(quarantined),
+        )
+        .await?;
+    }
+
+    if job.is_cancelled() {
+        return Err("Cache verification cancelled".to_string());
+    }
+    Ok(())
+}
+
+/// Move a corrupted download into quarantine and drop its index entries
+async fn quarantine_download(
+    quarantine: &mut Quarantine,
+    download_cache: &mut DownloadCache,
+    record: &mut CleanupRecordBuilder,
+    task: &VerifyTask,
+    actual_checksum: Option<String>,
+    size: u64,
+    reason: &str,
+) -> Result<(), String> {
+    quarantine
+        .add(task, actual_checksum, size, reason)
+        .await
+        .map_err(|e| e.to_string())?;
+    download_cache
+        .remove(&task.expected_checksum)
+        .await
+        .map_err(|e| e.to_string())?;
+    record.add_file(task.path.display().to_string(), size, "download");
+    Ok(())
+}
+
+fn quarantine_description(issue: String, outcome: &Result<(), String>) -> String {
+    match outcome {
+        Ok(()) => format!("{}; moved to quarantine", issue),
+        Err(e) => format!("{}; could not be quarantined: {}", issue, e),
+    }
+}
+
+/// List corrupted cache files moved into quarantine by `cache_verify`
+#[tauri::command]
+pub async fn cache_quarantine_list(
+    settings: State<'_, SharedSettings>,
+) -> Result<Vec<QuarantineEntry>, String> {
+    let cache_dir = settings.read().await.get_cache_dir();
+    let quarantine = Quarantine::open(&cache_dir)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(quarantine.list().to_vec())
+}
+
+/// Move a quarantined file back into the download cache and re-index it.
+/// A file that is still corrupted is quarantined again by the next verification.
+#[tauri::command]
+pub async fn cache_quarantine_restore(
+    id: String,
+    app: AppHandle,
+    settings: State<'_, SharedSettings>,
+) -> Result<QuarantineEntry, String> {
+    let cache_dir = settings.read().await.get_cache_dir();
+    let mut quarantine = Quarantine::open(&cache_dir)
+        .await
+        .map_err(|e| e.to_string())?;
+    let entry = quarantine.restore(&id).await.map_err(|e| e.to_string())?;
+
+    let mut download_cache = DownloadCache::open(&cache_dir)
+        .await
+        .map_err(|e| e.to_string())?;
+    download_cache
+        .register_file(
+            &entry.entry_keys,
+            Path::new(&entry.original_path),
+            &entry.expected_checksum,
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    emit_cache_changed(
+        &app,
+        "quarantine_restore",
+        0,
+        CacheCommandScope::Download.as_str(),
+        internal_cache_domains(),
+    );
+    Ok(entry)
+}
+
+#[derive(Serialize)]
+pub struct CacheQuarantinePurgeResult {
+    pub purged_count: usize,
+    pub freed_bytes: u64,
+    pub freed_human: String,
+}
+
+/// Permanently delete quarantined files, all of them when `ids` is omitted
+#[tauri::command]
+pub async fn cache_quarantine_purge(
+    ids: Option<Vec<String>>,
+    settings: State<'_, SharedSettings>,
+) -> Result<CacheQuarantinePurgeResult, String> {
+    let cache_dir = settings.read().await.get_cache_dir();
+    let mut quarantine = Quarantine::open(&cache_dir)
+        .await
+        .map_err(|e| e.to_string())?;
+    let purged = quarantine
+        .purge(ids.as_deref())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut record = CleanupRecordBuilder::new("quarantine_purge", false);
+    for entry in &purged {
+        record.add_file(&entry.quarantined_path, entry.size, "quarantine");
+    }
+    let record = record.build();
+    let freed_bytes = record.freed_bytes;
+    persist_cleanup_record(&cache_dir, record).await?;
+
+    Ok(CacheQuarantinePurgeResult {
+        purged_count: purged.len(),
+        freed_bytes,
+        freed_human: format_size(freed_bytes),
     })
 }
 
@@ -985,6 +1303,8 @@ pub struct CacheSettings {
     #[serde(default)]
     pub monitor_external: bool,
     #[serde(default)]
+    pub monthly_summary: bool,
+    #[serde(default)]
     pub external_cache_excluded_providers: Vec<String>,
     #[serde(default)]
     pub custom_cache_entries: Vec<CustomCacheEntry>,
@@ -1010,6 +1330,7 @@ pub async fn get_cache_settings(
         auto_clean_threshold: s.general.cache_auto_clean_threshold,
         monitor_interval: s.general.cache_monitor_interval,
         monitor_external: s.general.cache_monitor_external,
+        monthly_summary: s.general.cache_monthly_summary,
         external_cache_excluded_providers: s.general.external_cache_excluded_providers.clone(),
         custom_cache_entries: s.general.custom_cache_entries.clone(),
     })
@@ -1028,6 +1349,7 @@ pub async fn set_cache_settings(
     s.general.cache_auto_clean_threshold = new_settings.auto_clean_threshold;
     s.general.cache_monitor_interval = new_settings.monitor_interval;
     s.general.cache_monitor_external = new_settings.monitor_external;
+    s.general.cache_monthly_summary = new_settings.monthly_summary;
     s.general.external_cache_excluded_providers = new_settings.external_cache_excluded_providers;
     s.general.custom_cache_entries = new_settings.custom_cache_entries;
     s.save().await.map_err(|e| e.to_string())?;
@@ -1064,6 +1386,7 @@ async fn append_partial_preview(
             size_human: format_size(size),
             entry_type: "partial".to_string(),
             created_at: format_timestamp(partial.last_updated),
+            referenced_by: Vec::new(),
         });
     }
 
@@ -1264,6 +1587,8 @@ pub struct CleanPreviewItem {
     pub size_human: String,
     pub entry_type: String,
     pub created_at: String,
+    /// Installed environments (`env@version`) still using this download
+    pub referenced_by: Vec<String>,
 }
 
 /// Preview result for cache cleanup
@@ -1273,11 +1598,45 @@ pub struct CleanPreview {
     pub total_count: usize,
     pub total_size: u64,
     pub total_size_human: String,
+    /// Bytes that cleanup will actually free
+    pub safe_size: u64,
+    pub safe_size_human: String,
+    /// Bytes kept because installed environments reference them
+    pub referenced_size: u64,
+    pub referenced_size_human: String,
     pub skipped: Vec<DefaultDownloadsSkipItem>,
     pub skipped_count: usize,
 }
 
+/// `env@version` labels referencing `checksum`, in stable order.
+fn reference_labels(
+    references: &HashMap<String, Vec<CacheReference>>,
+    checksum: &str,
+) -> Vec<String> {
+    references
+        .get(checksum)
+        .map(|refs| refs.iter().map(CacheReference::label).collect())
+        .unwrap_or_default()
+}
+
+fn download_preview_item(
+    entry: CacheEntry,
+    references: &HashMap<String, Vec<CacheReference>>,
+) -> CleanPreviewItem {
+    CleanPreviewItem {
+        path: entry.file_path.display().to_string(),
+        size: entry.size,
+        size_human: format_size(entry.size),
+        entry_type: "download".to_string(),
+        created_at: entry.created_at.to_rfc3339(),
+        referenced_by: reference_labels(references, &entry.checksum),
+    }
+}
+
 /// Preview files that would be cleaned without actually deleting them
+///
+/// Downloads referenced by installed environments are listed but kept by
+/// cleanup; their bytes are reported separately as `referenced_size`.
 #[tauri::command]
 pub async fn cache_clean_preview(
     clean_type: Option<String>,
@@ -1314,13 +1673,7 @@ pub async fn cache_clean_preview(
                 .map_err(|e| e.to_string())?
             {
                 total_size += entry.size;
-                files.push(CleanPreviewItem {
-                    path: entry.file_path.display().to_string(),
-                    size: entry.size,
-                    size_human: format_size(entry.size),
-                    entry_type: "download".to_string(),
-                    created_at: entry.created_at.to_rfc3339(),
-                });
+                files.push(download_preview_item(entry, &references));
             }
             total_size +=
                 append_partial_preview(&mut files, &mut resumer, Duration::from_secs(0)).await?;
@@ -1338,6 +1691,7 @@ pub async fn cache_clean_preview(
                     size_human: format_size(entry.size),
                     entry_type: "metadata".to_string(),
                     created_at: entry.created_at.to_rfc3339(),
+                    referenced_by: Vec::new(),
                 });
             }
         }
@@ -1351,6 +1705,7 @@ pub async fn cache_clean_preview(
                     size_human: format_size(candidate.size),
                     entry_type: "default_download".to_string(),
                     created_at: candidate.created_at.to_rfc3339(),
+                    referenced_by: Vec::new(),
                 });
             }
             skipped = default_downloads.skipped;
@@ -1362,13 +1717,7 @@ pub async fn cache_clean_preview(
                 .map_err(|e| e.to_string())?
             {
                 total_size += entry.size;
-                files.push(CleanPreviewItem {
-                    path: entry.file_path.display().to_string(),
-                    size: entry.size,
-                    size_human: format_size(entry.size),
-                    entry_type: "download".to_string(),
-                    created_at: entry.created_at.to_rfc3339(),
-                });
+                files.push(download_preview_item(entry, &references));
             }
             for entry in metadata_cache
                 .preview_expired()
@@ -1382,6 +1731,7 @@ pub async fn cache_clean_preview(
                     size_human: format_size(entry.size),
                     entry_type: "metadata".to_string(),
                     created_at: entry.created_at.to_rfc3339(),
+                    referenced_by: Vec::new(),
                 });
             }
             total_size += append_partial_preview(&mut files, &mut resumer, max_age).await?;
@@ -1394,13 +1744,7 @@ pub async fn cache_clean_preview(
                 .map_err(|e| e.to_string())?
             {
                 total_size += entry.size;
-                files.push(CleanPreviewItem {
-                    path: entry.file_path.display().to_string(),
-                    size: entry.size,
-                    size_human: format_size(entry.size),
-                    entry_type: "download".to_string(),
-                    created_at: entry.created_at.to_rfc3339(),
-                });
+                files.push(download_preview_item(entry, &references));
             }
             for entry in metadata_cache
                 .preview_clean()
@@ -1414,6 +1758,7 @@ pub async fn cache_clean_preview(
                     size_human: format_size(entry.size),
                     entry_type: "metadata".to_string(),
                     created_at: entry.created_at.to_rfc3339(),
+                    referenced_by: Vec::new(),
                 });
             }
             total_size +=
@@ -1423,12 +1768,22 @@ pub async fn cache_clean_preview(
 
     let total_count = files.len();
     let skipped_count = skipped.len();
+    let referenced_size: u64 = files
+        .iter()
+        .filter(|item| !item.referenced_by.is_empty())
+        .map(|item| item.size)
+        .sum();
+    let safe_size = total_size.saturating_sub(referenced_size);
 
     Ok(CleanPreview {
         files,
         total_count,
         total_size,
         total_size_human: format_size(total_size),
+        safe_size,
+        safe_size_human: format_size(safe_size),
+        referenced_size,
+        referenced_size_human: format_size(referenced_size),
         skipped,
         skipped_count,
     })
@@ -1652,6 +2007,34 @@ pub async fn get_cleanup_summary(
     })
 }
 
+/// Aggregate cleanup history and usage samples into analytics for the cache page
+#[tauri::command]
+pub async fn cleanup_analytics(
+    period: Option<CleanupAnalyticsPeriod>,
+    settings: State<'_, SharedSettings>,
+) -> Result<CleanupAnalytics, String> {
+    let s = settings.read().await;
+    let cache_dir = s.get_cache_dir();
+    let max_size = s.general.cache_max_size;
+    drop(s);
+
+    let period = period.unwrap_or_default();
+    let history = CleanupHistory::open(&cache_dir)
+        .await
+        .map_err(|e| e.to_string())?;
+    let download_cache = DownloadCache::open(&cache_dir)
+        .await
+        .map_err(|e| e.to_string())?;
+    let samples = download_cache
+        .get_usage_samples(period.days().unwrap_or(90))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    analytics::cleanup_analytics(history.records().to_vec(), samples, max_size, period)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 // ==================== Cache Access Stats ====================
 
 /// Get cache access statistics (hit rate, hits, misses)
@@ -1701,6 +2084,8 @@ pub struct CacheEntryItem {
     pub created_at: String,
     pub last_accessed: Option<String>,
     pub hit_count: u32,
+    /// Installed environments (`env@version`) still using this entry
+    pub referenced_by: Vec<String>,
 }
 
 /// Result of listing cache entries
@@ -1751,9 +2136,15 @@ pub async fn list_cache_entries(
         .await
         .map_err(|e| e.to_string())?;
 
+    let references = download_cache
+        .references_by_checksum()
+        .await
+        .map_err(|e| e.to_string())?;
+
     let items: Vec<CacheEntryItem> = entries
         .into_iter()
         .map(|e| CacheEntryItem {
+            referenced_by: reference_labels(&references, &e.checksum),
             key: e.key,
             file_path: e.file_path.display().to_string(),
             size: e.size,
@@ -2015,17 +2406,45 @@ pub async fn calculate_external_cache_size(
         .map_err(|e| e.to_string())
 }
 
+/// Record successful external cleans in the cleanup history, one record per
+/// provider so analytics can attribute the freed space.
+async fn record_external_cleans(
+    settings: &SharedSettings,
+    use_trash: bool,
+    results: &[ExternalCacheCleanResult],
+) {
+    let cache_dir = settings.read().await.get_cache_dir();
+    for result in results
+        .iter()
+        .filter(|result| result.success && result.freed_bytes > 0)
+    {
+        let path = external::ExternalCacheProvider::parse_str(&result.provider)
+            .and_then(|provider| provider.cache_path())
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|| format!("external://{}", result.provider));
+        let mut builder =
+            CleanupRecordBuilder::new(format!("external_clean:{}", result.provider), use_trash);
+        builder.add_file(path, result.freed_bytes, "external");
+        if let Err(e) = persist_cleanup_record(&cache_dir, builder.build()).await {
+            log::warn!("Failed to record external clean: {}", e);
+        }
+    }
+}
+
 /// Clean cache for a specific external package manager
 #[tauri::command]
 pub async fn clean_external_cache(
     provider: String,
     use_trash: bool,
     app: AppHandle,
+    settings: State<'_, SharedSettings>,
 ) -> Result<ExternalCacheCleanResult, String> {
     let result = external::clean_cache(&provider, use_trash)
         .await
         .map_err(|e| e.to_string())?;
 
+    record_external_cleans(&settings, use_trash, std::slice::from_ref(&result)).await;
+
     if result.success {
         emit_external_cache_changed(
             &app,
@@ -2043,11 +2462,14 @@ pub async fn clean_external_cache(
 pub async fn clean_all_external_caches(
     use_trash: bool,
     app: AppHandle,
+    settings: State<'_, SharedSettings>,
 ) -> Result<Vec<ExternalCacheCleanResult>, String> {
     let results = external::clean_all_caches(use_trash)
         .await
         .map_err(|e| e.to_string())?;
 
+    record_external_cleans(&settings, use_trash, &results).await;
+
     let total_freed = results
         .iter()
         .filter(|result| result.success)
@@ -2182,6 +2604,18 @@ pub async fn cache_size_monitor(
 
     let exceeds_threshold = threshold > 0 && usage_percent >= threshold as f32;
 
+    // Persist a usage sample for growth projections (throttled in the db)
+    if let Err(e) = download_cache
+        .record_usage_sample(
+            internal_size,
+            include_ext.then_some(external_size),
+            total_size,
+        )
+        .await
+    {
+        log::warn!("Failed to record cache usage sample: {}", e);
+    }
+
     // Get disk space info
     let (disk_total, disk_available) = match disk::get_disk_space(&cache_dir).await {
         Ok(space) => (space.total, space.available),
@@ -2401,12 +2835,66 @@ pub async fn cache_migration_validate(
 }
 
 /// Execute cache migration
+///
+/// Copies and verifies the cache at the destination, switches the configured
+/// cache path (Move mode), and only then removes the source. Progress is
+/// emitted as `cache-migration-progress` events; an interrupted migration
+/// resumes from its journal when run again.
 #[tauri::command]
 pub async fn cache_migrate(
     destination: String,
     mode: String,
+    keep_source: Option<bool>,
+    verify_hashes: Option<bool>,
     app: AppHandle,
     settings: State<'_, SharedSettings>,
+    jobs: State<'_, SharedOperationQueue>,
+) -> Result<MigrationResult, String> {
+    let job = jobs
+        .inner()
+        .start(
+            JobKind::CacheMigrate,
+            format!("Migrate cache to {}", destination),
+            serde_json::json!({
+                "destination": destination,
+                "mode": mode,
+                "keepSource": keep_source,
+                "verifyHashes": verify_hashes,
+            }),
+            None,
+        )
+        .await;
+
+    let result = run_cache_migrate(
+        destination,
+        mode,
+        keep_source,
+        verify_hashes,
+        app,
+        settings,
+        &job,
+    )
+    .await;
+    let outcome = match &result {
+        Ok(result) if !result.success => Err(result
+            .error
+            .clone()
+            .unwrap_or_else(|| "Cache migration failed".to_string())),
+        Ok(_) => Ok(()),
+        Err(e) => Err(e.clone()),
+    };
+    job.finish(&outcome).await;
+    result
+}
+
+async fn run_cache_migrate(
+    destination: String,
+    mode: String,
+    keep_source: Option<bool>,
+    verify_hashes: Option<bool>,
+    app: AppHandle,
+    settings: State<'_, SharedSettings>,
+    job: &JobHandle,
 ) -> Result<MigrationResult, String> {
     let s = settings.read().await;
     let source = s.get_cache_dir();
@@ -2424,22 +2912,67 @@ pub async fn cache_migrate(
             ))
         }
     };
+    let options = MigrationOptions {
+        keep_source: keep_source.unwrap_or(false),
+        verify_hashes: verify_hashes.unwrap_or(false),
+        cancel: Some(job.cancel_flag()),
+    };
 
-    let result = migration::migrate_cache(&source, &dest, migration_mode)
-        .await
-        .map_err(|e| e.to_string())?;
+    let progress_app = app.clone();
+    let on_progress = move |progress: &MigrationProgress| {
+        let _ = progress_app.emit("cache-migration-progress", progress);
+        job.progress(
+            Some(f64::from(progress.percent)),
+            Some(progress.message.clone()),
+        );
+    };
+
+    let mut result =
+        migration::copy_and_verify(&source, &dest, migration_mode, &options, &on_progress)
+            .await
+            .map_err(|e| e.to_string())?;
+    if !result.success {
+        return Ok(result);
+    }
+    if job.is_cancelled() {
+        result.success = false;
+        result.source_kept = true;
+        result.resumable = true;
+        result.error = Some(
+            "Migration cancelled after copying; the source cache is still in use. Run the migration again to finish.".to_string(),
+        );
+        return Ok(result);
+    }
 
-    // If migration succeeded with Move mode, update the config to point to new path
-    if result.success && migration_mode == MigrationMode::Move {
+    // Switch the configured path before the source is removed, so a failure
+    // here leaves the old cache in place and in use
+    if migration_mode == MigrationMode::Move {
         let mut s = settings.write().await;
-        s.paths.cache = Some(dest.clone());
+        let previous = s.paths.cache.replace(dest.clone());
         if let Err(e) = s.save().await {
-            // Migration succeeded but config save failed - warn but don't fail
-            log::warn!("Cache migrated but config update failed: {}", e);
+            s.paths.cache = previous;
+            result.success = false;
+            result.source_kept = true;
+            result.resumable = true;
+            result.error = Some(format!(
+                "Files copied but config update failed: {}. The source cache was kept; run the migration again to finish.",
+                e
+            ));
+            return Ok(result);
         }
     }
     // For MoveAndLink mode, the old path still works via symlink, no config change needed
 
+    let result = migration::complete_migration(
+        &source,
+        &dest,
+        migration_mode,
+        &options,
+        result,
+        &on_progress,
+    )
+    .await;
+
     if result.success {
         let active_cache_dir = if migration_mode == MigrationMode::Move {
             dest.clone()
@@ -2550,17 +3083,53 @@ pub async fn cache_force_clean(
     })
 }
 
-/// Force clean a specific external cache provider via command or direct delete
+/// Preview a force clean of an external cache provider.
+///
+/// Returns per-path sizes, the regeneration cost, the available clean modes
+/// and the token required by `cache_force_clean_external`.
+#[tauri::command]
+pub async fn clean_external_cache_preview(
+    provider: String,
+) -> Result<external_preview::ExternalCleanPreview, String> {
+    let provider_enum = external::ExternalCacheProvider::parse_str(&provider)
+        .ok_or_else(|| format!("Unknown provider: {}", provider))?;
+    Ok(external_preview::build_preview(provider_enum).await)
+}
+
+/// Force clean a specific external cache provider via command or direct delete.
+///
+/// Requires a token from `clean_external_cache_preview` of the same provider;
+/// `mode` selects a partial clean listed by the preview (default "full").
 #[tauri::command]
 pub async fn cache_force_clean_external(
     provider: String,
+    preview_token: String,
+    mode: Option<String>,
     use_command: Option<bool>,
     use_trash: Option<bool>,
+    settings: State<'_, SharedSettings>,
     app: AppHandle,
 ) -> Result<ExternalCacheCleanResult, String> {
     let provider_enum = external::ExternalCacheProvider::parse_str(&provider)
         .ok_or_else(|| format!("Unknown provider: {}", provider))?;
 
+    let mode = mode.unwrap_or_else(|| external_preview::FULL_CLEAN_MODE.to_string());
+    let partial_mode = if mode == external_preview::FULL_CLEAN_MODE {
+        None
+    } else {
+        Some(
+            external_preview::find_partial_mode(provider_enum, &mode).ok_or_else(|| {
+                format!(
+                    "Clean mode '{}' is not supported for {}",
+                    mode,
+                    provider_enum.id()
+                )
+            })?,
+        )
+    };
+    external_preview::consume_preview_token(&preview_token, provider_enum.id())
+        .map_err(|e| e.to_string())?;
+
     let use_command = use_command.unwrap_or(true);
     let use_trash = use_trash.unwrap_or(false);
 
@@ -2572,24 +3141,26 @@ pub async fn cache_force_clean_external(
     };
 
     let preserve_dir = provider_enum.should_preserve_dir();
+    let mut removed_paths = Vec::new();
 
-    // Use command if requested and available, otherwise filesystem clean
-    let clean_result = if use_command {
-        if let Some((cmd, args)) = provider_enum.clean_command() {
-            let opts = Some(ProcessOptions {
-                timeout: Some(Duration::from_secs(120)),
-                ..Default::default()
-            });
-            match crate::platform::process::execute(cmd, args, opts).await {
-                Ok(output) if output.success => Ok(()),
-                Ok(output) => Err(format!("Command failed: {}", output.stderr)),
-                Err(e) => Err(format!("Command error: {}", e)),
-            }
-        } else {
-            fs_clean_path(&cache_path, preserve_dir, use_trash).await
+    let clean_result = match partial_mode.map(|m| m.action) {
+        Some(external_preview::PartialCleanAction::Command(cmd, args)) => {
+            run_external_clean_command(cmd, args).await
         }
-    } else {
-        fs_clean_path(&cache_path, preserve_dir, use_trash).await
+        Some(action) => match cache_path {
+            Some(ref path) if path.exists() => {
+                external_preview::clean_partial_paths(action, path, use_trash)
+                    .await
+                    .map(|removed| removed_paths = removed)
+                    .map_err(|e| e.to_string())
+            }
+            _ => Ok(()),
+        },
+        // Use command if requested and available, otherwise filesystem clean
+        None => match provider_enum.clean_command() {
+            Some((cmd, args)) if use_command => run_external_clean_command(cmd, args).await,
+            _ => fs_clean_path(&cache_path, preserve_dir, use_trash).await,
+        },
     };
 
     let size_after = if let Some(ref path) = cache_path {
@@ -2603,6 +3174,26 @@ pub async fn cache_force_clean_external(
         Ok(()) => {
             external::invalidate_discovery_cache().await;
             external::invalidate_provider_size_cache(provider_enum.id()).await;
+
+            let mut builder = CleanupRecordBuilder::new(
+                format!("external_force_clean:{}", provider_enum.id()),
+                use_trash,
+            );
+            builder.mode(mode.as_str());
+            if removed_paths.is_empty() {
+                if let Some(ref path) = cache_path {
+                    builder.add_file(path.display().to_string(), freed, "external");
+                }
+            } else {
+                for (path, size) in &removed_paths {
+                    builder.add_file(path.display().to_string(), *size, "external");
+                }
+            }
+            let cache_dir = settings.read().await.get_cache_dir();
+            if let Err(e) = persist_cleanup_record(&cache_dir, builder.build()).await {
+                log::warn!("Failed to record external force clean: {}", e);
+            }
+
             let result = ExternalCacheCleanResult {
                 provider: provider_enum.id().to_string(),
                 display_name: provider_enum.display_name().to_string(),
@@ -2630,6 +3221,18 @@ pub async fn cache_force_clean_external(
     }
 }
 
+async fn run_external_clean_command(cmd: &str, args: &[&str]) -> Result<(), String> {
+    let opts = Some(ProcessOptions {
+        timeout: Some(Duration::from_secs(120)),
+        ..Default::default()
+    });
+    match crate::platform::process::execute(cmd, args, opts).await {
+        Ok(output) if output.success => Ok(()),
+        Ok(output) => Err(format!("Command failed: {}", output.stderr)),
+        Err(e) => Err(format!("Command error: {}", e)),
+    }
+}
+
 // ============================================================================
 // External Cache Path Query
 // ============================================================================
@@ -2751,6 +3354,9 @@ fn get_provider_env_vars(provider: Option<external::ExternalCacheProvider>) -> V
         external::ExternalCacheProvider::Cypress => vec!["CYPRESS_CACHE_FOLDER".into()],
         external::ExternalCacheProvider::Electron => vec!["ELECTRON_CACHE".into()],
         external::ExternalCacheProvider::Vcpkg => vec!["VCPKG_DEFAULT_BINARY_CACHE".into()],
+        external::ExternalCacheProvider::Xmake => {
+            vec!["XMAKE_PKG_CACHEDIR".into(), "XMAKE_GLOBALDIR".into()]
+        }
         external::ExternalCacheProvider::Sbt => vec!["SBT_IVY_HOME".into()],
         _ => vec![],
     }
@@ -3042,6 +3648,7 @@ fn cache_settings_serde_round_trip_preserves_support_configuration() {
             auto_clean_threshold: 80,
             monitor_interval: 300,
             monitor_external: true,
+            monthly_summary: false,
             external_cache_excluded_providers: vec!["gradle".to_string(), "maven".to_string()],
             custom_cache_entries: vec![CustomCacheEntry {
                 id: "custom_docs".to_string(),
@@ -0,0 +1,74 @@
+use crate::provider::chocolatey::{ChocoPin, ChocoSource, ChocolateyProvider};
+use crate::SharedRegistry;
+use tauri::State;
+
+/// Helper to get the ChocolateyProvider from the registry.
+async fn get_choco(registry: &State<'_, SharedRegistry>) -> Result<ChocolateyProvider, String> {
+    let reg = registry.read().await;
+    if reg.get("chocolatey").is_none() {
+        return Err("chocolatey provider not registered".into());
+    }
+    Ok(ChocolateyProvider::new())
+}
+
+// ── Source management ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub async fn choco_source_list(
+    registry: State<'_, SharedRegistry>,
+) -> Result<Vec<ChocoSource>, String> {
+    let provider = get_choco(&registry).await?;
+    provider.source_list().await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn choco_source_add(
+    name: String,
+    url: String,
+    priority: Option<i32>,
+    registry: State<'_, SharedRegistry>,
+) -> Result<(), String> {
+    let provider = get_choco(&registry).await?;
+    provider
+        .source_add(&name, &url, priority)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn choco_source_remove(
+    name: String,
+    registry: State<'_, SharedRegistry>,
+) -> Result<(), String> {
+    let provider = get_choco(&registry).await?;
+    provider
+        .source_remove(&name)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// ── Pin management ──────────────────────────────────────────────────────
+
+#[tauri::command]
+pub async fn choco_pin_list(registry: State<'_, SharedRegistry>) -> Result<Vec<ChocoPin>, String> {
+    let provider = get_choco(&registry).await?;
+    provider.pin_list().await.map_err(|e| e.to_string())
+}
+
+/// Pin `name` (at `version`, or its installed version) or, with
+/// `pinned: false`, remove its pin.
+#[tauri::command]
+pub async fn choco_pin(
+    name: String,
+    version: Option<String>,
+    pinned: bool,
+    registry: State<'_, SharedRegistry>,
+) -> Result<(), String> {
+    let provider = get_choco(&registry).await?;
+    let result = if pinned {
+        provider.pin_add(&name, version.as_deref()).await
+    } else {
+        provider.pin_remove(&name).await
+    };
+    result.map_err(|e| e.to_string())
+}
@@ -1,6 +1,9 @@
+use crate::core::conda_env_spec::{self, CondaEnvSpec, CondaPackageDiff, InstalledCondaPackage};
 use crate::error::{CogniaError, CogniaResult};
 use crate::platform::process::{self, ProcessOptions};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -113,12 +116,16 @@ pub async fn conda_env_list() -> Result<Vec<CondaEnvInfo>, String> {
     Ok(envs)
 }
 
-async fn detect_env_python(prefix: &str) -> Option<String> {
-    let python_bin = if cfg!(windows) {
-        std::path::PathBuf::from(prefix).join("python.exe")
+fn env_python_path(prefix: &str) -> PathBuf {
+    if cfg!(windows) {
+        PathBuf::from(prefix).join("python.exe")
     } else {
-        std::path::PathBuf::from(prefix).join("bin").join("python")
-    };
+        PathBuf::from(prefix).join("bin").join("python")
+    }
+}
+
+async fn detect_env_python(prefix: &str) -> Option<String> {
+    let python_bin = env_python_path(prefix);
 
     if !python_bin.exists() {
         return None;
@@ -261,6 +268,737 @@ pub async fn conda_clean(all: bool, packages: bool, tarballs: bool) -> Result<St
     run_conda(&args, 120).await.map_err(|e| e.to_string())
 }
 
+// ── Disk usage & orphan pruning ──
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CondaDirUsage {
+    pub size_bytes: u64,
+    pub file_count: u64,
+    /// Bytes in files hard-linked elsewhere (usually the pkgs cache); these are
+    /// not freed by removing the environment alone. Always 0 on Windows.
+    pub shared_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CondaEnvDiskUsage {
+    pub name: String,
+    pub prefix: String,
+    pub is_base: bool,
+    #[serde(flatten)]
+    pub usage: CondaDirUsage,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CondaPkgsCacheUsage {
+    pub path: String,
+    #[serde(flatten)]
+    pub usage: CondaDirUsage,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CondaDiskUsageReport {
+    /// Sorted by size, largest first. Base excludes `envs/` and the pkgs cache.
+    pub environments: Vec<CondaEnvDiskUsage>,
+    pub pkgs_caches: Vec<CondaPkgsCacheUsage>,
+    pub envs_bytes: u64,
+    pub pkgs_cache_bytes: u64,
+    pub computed_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CondaOrphanPackage {
+    pub name: String,
+    pub version: String,
+    pub build: Option<String>,
+    pub channel: Option<String>,
+    /// Package size as recorded in `conda-meta`
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CondaPrunePreview {
+    pub env_name: String,
+    pub prefix: String,
+    pub is_base: bool,
+    /// Package names from `conda env export --from-history`
+    pub explicit_specs: Vec<String>,
+    pub installed_count: usize,
+    pub orphans: Vec<CondaOrphanPackage>,
+    pub reclaimable_bytes: u64,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CondaPruneResult {
+    pub env_name: String,
+    pub removed: Vec<String>,
+    pub reclaimed_bytes: u64,
+    pub output: String,
+}
+
+/// Installed package metadata from `<prefix>/conda-meta/*.json`
+#[derive(Debug, Clone, Deserialize)]
+struct CondaMetaPackage {
+    name: String,
+    #[serde(default)]
+    version: String,
+    #[serde(default)]
+    build: Option<String>,
+    #[serde(default)]
+    channel: Option<String>,
+    #[serde(default)]
+    size: u64,
+    #[serde(default)]
+    depends: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct CondaEnvTarget {
+    name: String,
+    prefix: String,
+    is_base: bool,
+}
+
+fn json_string_list(value: &serde_json::Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Package name of a match spec: `conda-forge::numpy>=1.2` / `numpy 1.2.*` -> `numpy`
+fn spec_package_name(spec: &str) -> Option<String> {
+    let spec = spec.trim();
+    let spec = spec.rsplit_once("::").map_or(spec, |(_, rest)| rest);
+    let name: String = spec
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        .collect();
+    (!name.is_empty()).then(|| name.to_lowercase())
+}
+
+/// Installed packages not reachable from any explicitly requested spec.
+/// `protected` names are always kept (with their dependencies).
+fn find_orphan_packages(
+    explicit: &[String],
+    installed: &[CondaMetaPackage],
+    protected: &[&str],
+) -> Vec<CondaOrphanPackage> {
+    let by_name: HashMap<String, &CondaMetaPackage> = installed
+        .iter()
+        .map(|pkg| (pkg.name.to_lowercase(), pkg))
+        .collect();
+
+    let mut reachable = HashSet::new();
+    let mut queue: VecDeque<String> = explicit
+        .iter()
+        .filter_map(|spec| spec_package_name(spec))
+        .chain(protected.iter().map(|name| name.to_string()))
+        .collect();
+    while let Some(name) = queue.pop_front() {
+        if !reachable.insert(name.clone()) {
+            continue;
+        }
+        if let Some(pkg) = by_name.get(&name) {
+            queue.extend(pkg.depends.iter().filter_map(|dep| spec_package_name(dep)));
+        }
+    }
+
+    let mut orphans: Vec<CondaOrphanPackage> = installed
+        .iter()
+        .filter(|pkg| !reachable.contains(&pkg.name.to_lowercase()))
+        .map(|pkg| CondaOrphanPackage {
+            name: pkg.name.clone(),
+            version: pkg.version.clone(),
+            build: pkg.build.clone(),
+            channel: pkg.channel.clone(),
+            size_bytes: pkg.size,
+        })
+        .collect();
+    orphans.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then(a.name.cmp(&b.name)));
+    orphans
+}
+
+/// Names of conda packages listed in a full `conda env export --json`
+fn exported_package_names(export: &serde_json::Value) -> HashSet<String> {
+    export["dependencies"]
+        .as_array()
+        .map(|deps| {
+            deps.iter()
+                .filter_map(|dep| dep.as_str())
+                .filter_map(spec_package_name)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Whether a full export carries a `pip:` section
+fn export_has_pip_section(export: &serde_json::Value) -> bool {
+    export["dependencies"]
+        .as_array()
+        .is_some_and(|deps| deps.iter().any(|dep| dep.get("pip").is_some()))
+}
+
+fn walk_dir_usage(root: &Path, excluded: &[PathBuf]) -> CondaDirUsage {
+    let mut usage = CondaDirUsage::default();
+    let walker = walkdir::WalkDir::new(root)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|entry| !excluded.iter().any(|ex| entry.path() == ex.as_path()));
+    for entry in walker.filter_map(|entry| entry.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        usage.size_bytes += meta.len();
+        usage.file_count += 1;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            if meta.nlink() > 1 {
+                usage.shared_bytes += meta.len();
+            }
+        }
+    }
+    usage
+}
+
+/// Walk each root on its own blocking thread, excluding `excluded` subtrees.
+async fn walk_dirs_parallel(roots: Vec<(PathBuf, Vec<PathBuf>)>) -> Vec<CondaDirUsage> {
+    let handles = roots.into_iter().map(|(root, excluded)| {
+        tokio::task::spawn_blocking(move || walk_dir_usage(&root, &excluded))
+    });
+    futures::future::join_all(handles)
+        .await
+        .into_iter()
+        .map(|result| result.unwrap_or_default())
+        .collect()
+}
+
+async fn conda_info_json() -> CogniaResult<serde_json::Value> {
+    let out = run_conda(&["info", "--json"], 30).await?;
+    serde_json::from_str(&out)
+        .map_err(|e| CogniaError::Parse(format!("Failed to parse conda info: {}", e)))
+}
+
+/// Resolve an environment given by name or prefix path.
+async fn resolve_env_target(env: &str) -> CogniaResult<CondaEnvTarget> {
+    let info = conda_info_json().await?;
+    let root_prefix = info["root_prefix"].as_str().unwrap_or_default().to_string();
+    let envs = json_string_list(&info["envs"]);
+
+    let env = env.trim();
+    let prefix = if env == "base" {
+        Some(root_prefix.clone())
+    } else if env.contains('/') || env.contains('\\') {
+        envs.iter()
+            .find(|p| Path::new(p) == Path::new(env))
+            .cloned()
+    } else {
+        envs.iter()
+            .find(|p| {
+                Path::new(p)
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy() == env)
+                    && **p != root_prefix
+            })
+            .cloned()
+    }
+    .filter(|p| !p.is_empty())
+    .ok_or_else(|| CogniaError::Provider(format!("Conda environment '{}' not found", env)))?;
+
+    let is_base = prefix == root_prefix;
+    Ok(CondaEnvTarget {
+        name: if is_base {
+            "base".to_string()
+        } else {
+            Path::new(&prefix)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| env.to_string())
+        },
+        prefix,
+        is_base,
+    })
+}
+
+async fn read_conda_meta(prefix: &str) -> CogniaResult<Vec<CondaMetaPackage>> {
+    let meta_dir = Path::new(prefix).join("conda-meta");
+    let mut entries = tokio::fs::read_dir(&meta_dir).await?;
+    let mut packages = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let Ok(content) = tokio::fs::read_to_string(&path).await else {
+            continue;
+        };
+        match serde_json::from_str::<CondaMetaPackage>(&content) {
+            Ok(pkg) => packages.push(pkg),
+            Err(e) => log::debug!("Skipping unreadable conda-meta {:?}: {}", path, e),
+        }
+    }
+    Ok(packages)
+}
+
+async fn build_prune_preview(env: &str, allow_base: bool) -> CogniaResult<CondaPrunePreview> {
+    let target = resolve_env_target(env).await?;
+    if target.is_base && !allow_base {
+        return Err(CogniaError::Provider(
+            "Refusing to prune the base environment without allowBase".into(),
+        ));
+    }
+
+    let history_out = run_conda(
+        &[
+            "env",
+            "export",
+            "-p",
+            &target.prefix,
+            "--from-history",
+            "--json",
+        ],
+        60,
+    )
+    .await?;
+    let history: serde_json::Value = serde_json::from_str(&history_out)
+        .map_err(|e| CogniaError::Parse(format!("Failed to parse conda export: {}", e)))?;
+    let explicit_specs = json_string_list(&history["dependencies"]);
+
+    let full_out = run_conda(&["env", "export", "-p", &target.prefix, "--json"], 60).await?;
+    let full: serde_json::Value = serde_json::from_str(&full_out)
+        .map_err(|e| CogniaError::Parse(format!("Failed to parse conda export: {}", e)))?;
+    let exported = exported_package_names(&full);
+
+    // Only consider packages conda itself reports as installed
+    let installed: Vec<CondaMetaPackage> = read_conda_meta(&target.prefix)
+        .await?
+        .into_iter()
+        .filter(|pkg| exported.is_empty() || exported.contains(&pkg.name.to_lowercase()))
+        .collect();
+
+    let mut warnings = Vec::new();
+    let mut protected = Vec::new();
+    if export_has_pip_section(&full) {
+        protected.extend(["pip", "python"]);
+        warnings.push("pip-installed packages are not analysed; pip and python are kept".into());
+    }
+    if target.is_base {
+        protected.extend(["conda", "mamba", "conda-libmamba-solver"]);
+    }
+
+    let orphans = if explicit_specs.is_empty() {
+        warnings.push(
+            "No explicit install history for this environment; nothing can be safely pruned".into(),
+        );
+        Vec::new()
+    } else {
+        find_orphan_packages(&explicit_specs, &installed, &protected)
+    };
+
+    Ok(CondaPrunePreview {
+        env_name: target.name,
+        prefix: target.prefix,
+        is_base: target.is_base,
+        explicit_specs: explicit_specs
+            .iter()
+            .filter_map(|spec| spec_package_name(spec))
+            .collect(),
+        installed_count: installed.len(),
+        reclaimable_bytes: orphans.iter().map(|o| o.size_bytes).sum(),
+        orphans,
+        warnings,
+    })
+}
+
+/// Per-environment disk usage with the shared pkgs cache reported separately
+#[tauri::command]
+pub async fn conda_env_disk_usage() -> Result<CondaDiskUsageReport, String> {
+    let info = conda_info_json().await.map_err(|e| e.to_string())?;
+    let root_prefix = info["root_prefix"].as_str().unwrap_or_default().to_string();
+    let envs = json_string_list(&info["envs"]);
+    let pkgs_dirs: Vec<PathBuf> = json_string_list(&info["pkgs_dirs"])
+        .into_iter()
+        .map(PathBuf::from)
+        .filter(|p| p.is_dir())
+        .collect();
+    let envs_dirs: Vec<PathBuf> = json_string_list(&info["envs_dirs"])
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+
+    let env_prefixes: Vec<PathBuf> = envs.iter().map(PathBuf::from).collect();
+    let mut roots = Vec::new();
+    for prefix in &env_prefixes {
+        // Other environments and package caches nested under this prefix
+        // (typically base) are attributed to themselves, not to the parent.
+        let excluded: Vec<PathBuf> = env_prefixes
+            .iter()
+            .chain(pkgs_dirs.iter())
+            .chain(envs_dirs.iter())
+            .filter(|other| *other != prefix && other.starts_with(prefix))
+            .cloned()
+            .collect();
+        roots.push((prefix.clone(), excluded));
+    }
+    for pkgs in &pkgs_dirs {
+        roots.push((pkgs.clone(), Vec::new()));
+    }
+
+    let mut usages = walk_dirs_parallel(roots).await.into_iter();
+    let mut environments: Vec<CondaEnvDiskUsage> = envs
+        .iter()
+        .map(|prefix| {
+            let is_base = *prefix == root_prefix;
+            CondaEnvDiskUsage {
+                name: if is_base {
+                    "base".to_string()
+                } else {
+                    Path::new(prefix)
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_else(|| prefix.clone())
+                },
+                prefix: prefix.clone(),
+                is_base,
+                usage: usages.next().unwrap_or_default(),
+            }
+        })
+        .collect();
+    environments.sort_by(|a, b| b.usage.size_bytes.cmp(&a.usage.size_bytes));
+
+    let pkgs_caches: Vec<CondaPkgsCacheUsage> = pkgs_dirs
+        .iter()
+        .map(|path| CondaPkgsCacheUsage {
+            path: path.display().to_string(),
+            usage: usages.next().unwrap_or_default(),
+        })
+        .collect();
+
+    Ok(CondaDiskUsageReport {
+        envs_bytes: environments.iter().map(|e| e.usage.size_bytes).sum(),
+        pkgs_cache_bytes: pkgs_caches.iter().map(|c| c.usage.size_bytes).sum(),
+        environments,
+        pkgs_caches,
+        computed_at: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+/// Packages in `env` that no explicitly installed spec depends on
+#[tauri::command]
+pub async fn conda_env_prune_preview(
+    env: String,
+    allow_base: Option<bool>,
+) -> Result<CondaPrunePreview, String> {
+    build_prune_preview(&env, allow_base.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Remove the orphaned packages found by `conda_env_prune_preview`
+#[tauri::command]
+pub async fn conda_env_prune(
+    env: String,
+    allow_base: Option<bool>,
+) -> Result<CondaPruneResult, String> {
+    let preview = build_prune_preview(&env, allow_base.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())?;
+    if preview.orphans.is_empty() {
+        return Ok(CondaPruneResult {
+            env_name: preview.env_name,
+            removed: Vec::new(),
+            reclaimed_bytes: 0,
+            output: String::new(),
+        });
+    }
+
+    let removed: Vec<String> = preview.orphans.iter().map(|o| o.name.clone()).collect();
+    let mut args = vec!["remove", "-p", preview.prefix.as_str(), "-y"];
+    args.extend(removed.iter().map(String::as_str));
+    let output = run_conda(&args, 600).await.map_err(|e| e.to_string())?;
+
+    Ok(CondaPruneResult {
+        env_name: preview.env_name,
+        removed,
+        reclaimed_bytes: preview.reclaimable_bytes,
+        output,
+    })
+}
+
+// ── environment.yml diff & sync, conda-lock ──
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CondaEnvDiff {
+    pub env_name: String,
+    pub prefix: String,
+    pub spec_path: String,
+    /// Packages managed by conda
+    pub conda: CondaPackageDiff,
+    /// Packages from the spec's `pip:` section and pip-installed packages
+    pub pip: CondaPackageDiff,
+    /// Spec entries that could not be compared and are left untouched
+    pub unsupported_specs: Vec<String>,
+    pub warnings: Vec<String>,
+    /// No additions or changes, and no removals
+    pub in_sync: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CondaEnvSyncResult {
+    pub env_name: String,
+    /// The diff that was applied
+    pub diff: CondaEnvDiff,
+    pub output: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CondaLockInstallResult {
+    pub env_name: String,
+    /// `conda-lock` or `micromamba`
+    pub tool: String,
+    pub platform: String,
+    pub output: String,
+}
+
+async fn read_environment_yml(path: &str) -> CogniaResult<CondaEnvSpec> {
+    let content = tokio::fs::read_to_string(path).await?;
+    conda_env_spec::parse_environment_yml(&content)
+}
+
+/// Normalized names of pip packages no other pip package requires
+async fn pip_not_required(prefix: &str) -> CogniaResult<HashSet<String>> {
+    let python = env_python_path(prefix);
+    let out = process::execute(
+        python.to_string_lossy().as_ref(),
+        &["-m", "pip", "list", "--not-required", "--format", "json"],
+        Some(make_opts(60)),
+    )
+    .await?;
+    if !out.success {
+        return Err(CogniaError::Provider(out.stderr));
+    }
+    let packages: Vec<serde_json::Value> = serde_json::from_str(&out.stdout)
+        .map_err(|e| CogniaError::Parse(format!("Failed to parse pip list: {}", e)))?;
+    Ok(packages
+        .iter()
+        .filter_map(|pkg| pkg["name"].as_str())
+        .map(conda_env_spec::normalize_pip_name)
+        .collect())
+}
+
+async fn run_checked(program: &str, args: &[&str], timeout: u64) -> Result<String, String> {
+    let out = process::execute(program, args, Some(make_opts(timeout)))
+        .await
+        .map_err(|e| e.to_string())?;
+    if out.success {
+        Ok(out.stdout)
+    } else {
+        Err(if out.stderr.is_empty() {
+            out.stdout
+        } else {
+            out.stderr
+        })
+    }
+}
+
+async fn build_env_diff(env: &str, yaml_path: &str, prune: bool) -> CogniaResult<CondaEnvDiff> {
+    let spec = read_environment_yml(yaml_path).await?;
+    let target = resolve_env_target(env).await?;
+
+    let list_out = run_conda(&["list", "-p", &target.prefix, "--json"], 60).await?;
+    let installed: Vec<InstalledCondaPackage> = serde_json::from_str(&list_out)
+        .map_err(|e| CogniaError::Parse(format!("Failed to parse conda list: {}", e)))?;
+
+    let mut warnings = Vec::new();
+    let mut conda_removable = HashSet::new();
+    let mut pip_removable = HashSet::new();
+    if prune {
+        // Removal candidates: installed packages nothing in the spec needs
+        let explicit: Vec<String> = spec.conda.iter().map(|s| s.raw.clone()).collect();
+        let protected: &[&str] = if spec.pip.is_empty() {
+            &[]
+        } else {
+            &["pip", "python"]
+        };
+        let meta = read_conda_meta(&target.prefix).await?;
+        conda_removable = find_orphan_packages(&explicit, &meta, protected)
+            .into_iter()
+            .map(|orphan| orphan.name.to_lowercase())
+            .collect();
+        if installed.iter().any(|pkg| pkg.is_pip()) {
+            match pip_not_required(&target.prefix).await {
+                Ok(names) => pip_removable = names,
+                Err(e) => warnings.push(format!(
+                    "Could not list pip dependencies; pip packages are not removed: {}",
+                    e
+                )),
+            }
+        }
+    }
+
+    let conda = conda_env_spec::diff_conda_packages(&spec, &installed, &conda_removable);
+    let pip = conda_env_spec::diff_pip_packages(&spec, &installed, &pip_removable);
+    if !spec.unsupported.is_empty() {
+        warnings.push(format!(
+            "{} spec entries cannot be compared and are ignored",
+            spec.unsupported.len()
+        ));
+    }
+
+    Ok(CondaEnvDiff {
+        env_name: target.name,
+        prefix: target.prefix,
+        spec_path: yaml_path.to_string(),
+        in_sync: conda.is_empty() && pip.is_empty(),
+        conda,
+        pip,
+        unsupported_specs: spec.unsupported,
+        warnings,
+    })
+}
+
+/// Packages to add, change or remove so `env_name` matches an `environment.yml`
+#[tauri::command]
+pub async fn conda_env_diff(env_name: String, yaml_path: String) -> Result<CondaEnvDiff, String> {
+    build_env_diff(&env_name, &yaml_path, true)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Apply the `conda_env_diff` of `env_name` against an `environment.yml`.
+/// Removals are only applied with `prune`.
+#[tauri::command]
+pub async fn conda_env_sync(
+    env_name: String,
+    yaml_path: String,
+    prune: bool,
+) -> Result<CondaEnvSyncResult, String> {
+    let diff = build_env_diff(&env_name, &yaml_path, prune)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut output = String::new();
+
+    let conda_specs = diff.conda.install_specs();
+    if !conda_specs.is_empty() {
+        let spec = read_environment_yml(&yaml_path)
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut args = vec!["install", "-p", diff.prefix.as_str(), "-y"];
+        for channel in &spec.channels {
+            args.extend(["-c", channel.as_str()]);
+        }
+        args.extend(conda_specs.iter().map(String::as_str));
+        output.push_str(&run_conda(&args, 1800).await.map_err(|e| e.to_string())?);
+    }
+
+    let pip_specs = diff.pip.install_specs();
+    let python = env_python_path(&diff.prefix);
+    let python = python.to_string_lossy();
+    if !pip_specs.is_empty() {
+        let mut args = vec!["-m", "pip", "install"];
+        args.extend(pip_specs.iter().map(String::as_str));
+        output.push_str(&run_checked(&python, &args, 1800).await?);
+    }
+
+    let conda_removals = diff.conda.removal_names();
+    if !conda_removals.is_empty() {
+        let mut args = vec!["remove", "-p", diff.prefix.as_str(), "-y"];
+        args.extend(conda_removals.iter().map(String::as_str));
+        output.push_str(&run_conda(&args, 600).await.map_err(|e| e.to_string())?);
+    }
+
+    let pip_removals = diff.pip.removal_names();
+    if !pip_removals.is_empty() {
+        let mut args = vec!["-m", "pip", "uninstall", "-y"];
+        args.extend(pip_removals.iter().map(String::as_str));
+        output.push_str(&run_checked(&python, &args, 600).await?);
+    }
+
+    Ok(CondaEnvSyncResult {
+        env_name: diff.env_name.clone(),
+        diff,
+        output,
+    })
+}
+
+/// Create `env_name` from a unified `conda-lock.yml` with conda-lock, or
+/// micromamba when conda-lock is not installed. `platform` defaults to the
+/// running platform and must be locked in the file.
+#[tauri::command]
+pub async fn conda_lock_install(
+    lockfile: String,
+    env_name: String,
+    platform: Option<String>,
+) -> Result<CondaLockInstallResult, String> {
+    let content = tokio::fs::read_to_string(&lockfile)
+        .await
+        .map_err(|e| e.to_string())?;
+    let lock = conda_env_spec::parse_conda_lock(&content).map_err(|e| e.to_string())?;
+    let platform = platform.unwrap_or_else(conda_env_spec::current_conda_subdir);
+    if !lock.platforms.contains(&platform) {
+        return Err(format!(
+            "Platform '{}' is not locked in {} (locked: {})",
+            platform,
+            lockfile,
+            lock.platforms.join(", ")
+        ));
+    }
+
+    let (tool, output) = if process::which("conda-lock").await.is_some() {
+        let opts = make_opts(1800).with_env("CONDA_SUBDIR", &platform);
+        let out = process::execute(
+            "conda-lock",
+            &["install", "--name", &env_name, &lockfile],
+            Some(opts),
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+        if !out.success {
+            return Err(out.stderr);
+        }
+        ("conda-lock", out.stdout)
+    } else if process::which("micromamba").await.is_some() {
+        let args = [
+            "create",
+            "-y",
+            "-n",
+            env_name.as_str(),
+            "-f",
+            lockfile.as_str(),
+            "--platform",
+            platform.as_str(),
+        ];
+        ("micromamba", run_checked("micromamba", &args, 1800).await?)
+    } else {
+        return Err("Installing from a conda-lock file requires conda-lock or micromamba".into());
+    };
+
+    Ok(CondaLockInstallResult {
+        env_name,
+        tool: tool.to_string(),
+        platform,
+        output,
+    })
+}
+
 #[tauri::command]
 pub async fn conda_config_show() -> Result<String, String> {
     run_conda(&["config", "--show"], 15)
@@ -288,3 +1026,92 @@ pub async fn conda_channel_remove(channel: String) -> Result<String, String> {
         .await
         .map_err(|e| e.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(name: &str, size: u64, depends: &[&str]) -> CondaMetaPackage {
+        CondaMetaPackage {
+            name: name.to_string(),
+            version: "1.0".to_string(),
+            build: Some("py_0".to_string()),
+            channel: Some("conda-forge".to_string()),
+            size,
+            depends: depends.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_spec_package_name() {
+        assert_eq!(spec_package_name("numpy"), Some("numpy".into()));
+        assert_eq!(spec_package_name("python=3.11"), Some("python".into()));
+        assert_eq!(
+            spec_package_name("conda-forge::scikit-learn>=1.3"),
+            Some("scikit-learn".into())
+        );
+        assert_eq!(
+            spec_package_name("libgcc-ng >=12,<13.0a0"),
+            Some("libgcc-ng".into())
+        );
+        assert_eq!(spec_package_name("  "), None);
+    }
+
+    #[test]
+    fn test_find_orphan_packages() {
+        let installed = vec![
+            meta("python", 30, &["libffi >=3.4", "openssl >=3"]),
+            meta("libffi", 5, &[]),
+            meta("openssl", 8, &["ca-certificates"]),
+            meta("ca-certificates", 1, &[]),
+            meta("numpy", 20, &["python >=3.9", "libblas"]),
+            meta("libblas", 10, &[]),
+            // left behind after `conda remove pandas`
+            meta("pytz", 3, &["python"]),
+            meta("tzdata", 7, &[]),
+            meta("pip", 2, &["python"]),
+        ];
+        let explicit = vec!["python=3.11".to_string(), "numpy".to_string()];
+
+        let orphans = find_orphan_packages(&explicit, &installed, &[]);
+        let names: Vec<&str> = orphans.iter().map(|o| o.name.as_str()).collect();
+        assert_eq!(names, vec!["tzdata", "pytz", "pip"]);
+
+        let orphans = find_orphan_packages(&explicit, &installed, &["pip"]);
+        let names: Vec<&str> = orphans.iter().map(|o| o.name.as_str()).collect();
+        assert_eq!(names, vec!["tzdata", "pytz"]);
+    }
+
+    #[test]
+    fn test_export_parsing() {
+        let export = serde_json::json!({
+            "name": "ml",
+            "dependencies": [
+                "numpy=1.26.4=py311h_0",
+                "python=3.11.8=h_0",
+                { "pip": ["requests==2.31.0"] }
+            ]
+        });
+        let names = exported_package_names(&export);
+        assert!(names.contains("numpy") && names.contains("python"));
+        assert_eq!(names.len(), 2);
+        assert!(export_has_pip_section(&export));
+    }
+
+    #[test]
+    fn test_walk_dir_usage_excludes_nested_prefixes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("lib")).unwrap();
+        std::fs::create_dir_all(root.join("envs/ml")).unwrap();
+        std::fs::create_dir_all(root.join("pkgs")).unwrap();
+        std::fs::write(root.join("lib/a.so"), vec![0u8; 100]).unwrap();
+        std::fs::write(root.join("envs/ml/b.so"), vec![0u8; 50]).unwrap();
+        std::fs::write(root.join("pkgs/c.tar.bz2"), vec![0u8; 25]).unwrap();
+
+        let usage = walk_dir_usage(root, &[root.join("envs/ml"), root.join("pkgs")]);
+        assert_eq!(usage.size_bytes, 100);
+        assert_eq!(usage.file_count, 1);
+        assert_eq!(usage.shared_bytes, 0);
+    }
+}
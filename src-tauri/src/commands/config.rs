@@ -1,20 +1,44 @@
+use crate::commands::custom_detection::SharedCustomDetectionManager;
+use crate::commands::download::{setup_download_manager, SharedDownloadManager};
+use crate::commands::local_api::apply_local_api_server;
+use crate::commands::metrics::apply_metrics_listener;
 use crate::commands::package::{invalidate_package_caches, refresh_provider_registry};
-use crate::config::Settings;
+use crate::config::{
+    config_key_moves_data_dirs, config_key_rebuilds_registry, ConfigChange, ConfigImportPreview,
+    Settings, SettingsLoadReport,
+};
 use crate::core::system_info::BatteryInfo;
+use crate::core::team_config::{
+    self, TeamConfigPayload, TeamConfigPreview, TeamConfigSection, TeamConfigStatus,
+};
 use crate::platform::disk::format_size;
+use crate::platform::proxy::EffectiveProxy;
 use crate::SharedRegistry;
 use serde::Serialize;
 use std::sync::Arc;
-use tauri::State;
+use tauri::{AppHandle, Manager, State};
 use tokio::sync::RwLock;
 
 pub type SharedSettings = Arc<RwLock<Settings>>;
+/// Outcome of loading the config file at startup, held until the UI takes it
+pub type SharedSettingsLoadReport = Arc<RwLock<Option<SettingsLoadReport>>>;
 
 fn refresh_network_clients(settings: &Settings) {
     crate::platform::proxy::rebuild_shared_client(settings);
     crate::provider::api::update_api_client_from_settings(settings);
 }
 
+fn refresh_asset_rules(settings: &Settings) {
+    crate::download::set_user_asset_rules(crate::download::AssetRules::compile(
+        &settings.general.asset_match_rules,
+        &settings.general.asset_exclude_patterns,
+    ));
+}
+
+fn refresh_extract_symlink_mode(settings: &Settings) {
+    crate::core::archive::set_symlink_mode(settings.general.extract_symlink_mode);
+}
+
 fn should_refresh_network_clients_for_key(key: &str) -> bool {
     key.starts_with("network.") || key.starts_with("security.") || key.starts_with("mirrors.")
 }
@@ -36,24 +60,46 @@ pub async fn config_get(
 pub async fn config_set(
     key: String,
     value: String,
+    app: AppHandle,
     settings: State<'_, SharedSettings>,
     registry: State<'_, SharedRegistry>,
 ) -> Result<(), String> {
     let mut s = settings.write().await;
+    let previous_channel = s.updates.update_channel;
     s.set_value(&key, &value).map_err(|e| e.to_string())?;
     s.save().await.map_err(|e| e.to_string())?;
+    let channel_changed = s.updates.update_channel != previous_channel;
 
     if should_refresh_network_clients_for_key(&key) {
         refresh_network_clients(&s);
     }
+    if key.starts_with("general.asset_") {
+        refresh_asset_rules(&s);
+    }
+    if key == "general.extract_symlink_mode" {
+        refresh_extract_symlink_mode(&s);
+    }
 
     drop(s);
 
+    if key.starts_with("general.metrics_listener_") {
+        apply_metrics_listener(&app).await;
+    }
+    if key.starts_with("general.local_api_") {
+        apply_local_api_server(&app).await;
+    }
+
     if is_provider_config_key(&key) {
         refresh_provider_registry(settings.inner(), registry.inner()).await?;
         invalidate_package_caches(settings.inner()).await;
     }
 
+    // A pending update belonged to the old channel's feed; the next
+    // self_check_update re-evaluates it against the new channel.
+    if channel_changed {
+        crate::tray::set_has_update(&app, false).await?;
+    }
+
     Ok(())
 }
 
@@ -68,6 +114,7 @@ pub async fn config_set(
 /// Static configuration keys included in config_list output.
 const CONFIG_LIST_STATIC_KEYS: &[&str] = &[
     "general.parallel_downloads",
+    "general.parallel_env_installs",
     "general.resolve_strategy",
     "general.auto_update_metadata",
     "general.metadata_cache_ttl",
@@ -78,14 +125,32 @@ pub async fn config_set(
     "general.cache_auto_clean_threshold",
     "general.cache_monitor_interval",
     "general.cache_monitor_external",
+    "general.cache_monthly_summary",
     "general.download_speed_limit",
+    "general.download_monthly_cap_mb",
+    "general.download_history_max_age_days",
+    "general.download_history_max_records",
+    "general.trash_retention_days",
+    "general.download_auto_resume",
+    "general.progress_event_interval_ms",
+    "general.extract_symlink_mode",
+    "general.pin_file_format",
+    "general.python_tool_backend",
     "general.update_check_concurrency",
+    "general.metrics_listener_enabled",
+    "general.metrics_listener_port",
+    "general.local_api_enabled",
+    "general.local_api_rate_limit_per_minute",
     "general.external_cache_excluded_providers",
     "general.custom_cache_entries",
+    "general.asset_match_rules",
+    "general.asset_exclude_patterns",
+    "general.smoke_tests",
     "network.timeout",
     "network.retries",
     "network.proxy",
     "network.no_proxy",
+    "network.offline_mode",
     "security.allow_http",
     "security.verify_certificates",
     "security.allow_self_signed",
@@ -103,6 +168,7 @@ pub async fn config_set(
     "updates.source_mode",
     "updates.custom_endpoints",
     "updates.fallback_to_official",
+    "updates.update_channel",
     "tray.minimize_to_tray",
     "tray.start_minimized",
     "tray.show_notifications",
@@ -115,6 +181,7 @@ pub async fn config_set(
     "envvar.default_scope",
     "envvar.auto_snapshot",
     "envvar.mask_sensitive",
+    "envvar.secret_key_patterns",
     "paths.root",
     "paths.cache",
     "paths.environments",
@@ -125,6 +192,10 @@ pub async fn config_set(
     "terminal.proxy_mode",
     "terminal.custom_proxy",
     "terminal.no_proxy",
+    "terminal.launch_history_max",
+    "terminal.launch_history_record_env_values",
+    "terminal.launch_sample_interval_secs",
+    "terminal.terminate_launches_on_exit",
     "backup.auto_backup_enabled",
     "backup.auto_backup_interval_hours",
     "backup.max_backups",
@@ -138,6 +209,11 @@ pub async fn config_set(
     "shortcuts.toggle_window",
     "shortcuts.command_palette",
     "shortcuts.quick_search",
+    "notifications.download",
+    "notifications.update",
+    "notifications.eol",
+    "notifications.install_failure",
+    "notifications.system",
 ];
 
 pub fn collect_config_list(settings: &Settings) -> Vec<(String, String)> {
@@ -202,6 +278,14 @@ pub fn collect_config_list(settings: &Settings) -> Vec<(String, String)> {
         result.push((format!("{}.verify_ssl", key), config.verify_ssl.to_string()));
     }
 
+    // Add per-provider proxy overrides
+    for (provider, proxy) in &settings.network.provider_proxies {
+        result.push((
+            format!("network.provider_proxies.{}", provider),
+            proxy.clone(),
+        ));
+    }
+
     result
 }
 
@@ -213,17 +297,38 @@ pub async fn config_list(
     Ok(collect_config_list(&s))
 }
 
+/// Migration and validation warnings from loading the config file at
+/// startup. Returned once; `None` when there is nothing to report.
+#[tauri::command]
+pub async fn config_take_load_report(
+    report: State<'_, SharedSettingsLoadReport>,
+) -> Result<Option<SettingsLoadReport>, String> {
+    Ok(report.write().await.take())
+}
+
 #[tauri::command]
 pub fn config_list_defaults() -> Result<Vec<(String, String)>, String> {
     Ok(collect_config_list(&Settings::default()))
 }
 
 #[tauri::command]
-pub async fn config_reset(settings: State<'_, SharedSettings>) -> Result<(), String> {
+pub async fn config_reset(
+    app: AppHandle,
+    settings: State<'_, SharedSettings>,
+) -> Result<(), String> {
     let mut s = settings.write().await;
-    *s = Settings::default();
+    let onboarding = std::mem::take(&mut s.onboarding);
+    *s = Settings {
+        onboarding,
+        ..Settings::default()
+    };
     s.save().await.map_err(|e| e.to_string())?;
     refresh_network_clients(&s);
+    refresh_asset_rules(&s);
+    refresh_extract_symlink_mode(&s);
+    drop(s);
+    apply_metrics_listener(&app).await;
+    apply_local_api_server(&app).await;
     Ok(())
 }
 
@@ -243,9 +348,12 @@ pub async fn get_platform_info() -> Result<PlatformInfo, String> {
 
 #[cfg(test)]
 mod tests {
+    use super::collect_config_list;
     use super::config_list_defaults;
+    use super::default_proxy_test_url;
     use super::should_refresh_network_clients_for_key;
     use super::PlatformInfo;
+    use super::Settings;
     use super::CONFIG_LIST_STATIC_KEYS;
 
     #[test]
@@ -257,6 +365,36 @@ fn network_client_refresh_key_filter_covers_proxy_security_and_mirrors() {
         ));
         assert!(should_refresh_network_clients_for_key("mirrors.npm"));
         assert!(should_refresh_network_clients_for_key("mirrors.crates"));
+        assert!(should_refresh_network_clients_for_key(
+            "network.provider_proxies.github"
+        ));
+    }
+
+    #[test]
+    fn config_list_includes_provider_proxy_overrides() {
+        let mut settings = Settings::default();
+        settings
+            .network
+            .provider_proxies
+            .insert("github".into(), "socks5://127.0.0.1:1080".into());
+        let list = collect_config_list(&settings);
+        assert!(list.contains(&(
+            "network.provider_proxies.github".to_string(),
+            "socks5://127.0.0.1:1080".to_string()
+        )));
+    }
+
+    #[test]
+    fn default_proxy_test_url_targets_provider_registry() {
+        assert_eq!(
+            default_proxy_test_url(Some("GitHub")),
+            "https://api.github.com"
+        );
+        assert_eq!(
+            default_proxy_test_url(Some("yarn")),
+            "https://registry.npmjs.org"
+        );
+        assert_eq!(default_proxy_test_url(None), "https://www.google.com");
     }
 
     #[test]
@@ -293,6 +431,7 @@ fn config_list_static_keys_include_updates_and_tray_settings() {
         assert!(CONFIG_LIST_STATIC_KEYS.contains(&"updates.source_mode"));
         assert!(CONFIG_LIST_STATIC_KEYS.contains(&"updates.custom_endpoints"));
         assert!(CONFIG_LIST_STATIC_KEYS.contains(&"updates.fallback_to_official"));
+        assert!(CONFIG_LIST_STATIC_KEYS.contains(&"updates.update_channel"));
         assert!(CONFIG_LIST_STATIC_KEYS.contains(&"tray.minimize_to_tray"));
         assert!(CONFIG_LIST_STATIC_KEYS.contains(&"tray.start_minimized"));
         assert!(CONFIG_LIST_STATIC_KEYS.contains(&"tray.show_notifications"));
@@ -1305,26 +1444,257 @@ pub fn app_check_init() -> crate::StartupStatus {
     crate::get_startup_status()
 }
 
-/// Export the full backend config as a TOML string
+/// Export the full backend config as a TOML string. Provider tokens are
+/// exported only as references, never as values; onboarding progress is
+/// machine state and left out.
 #[tauri::command]
 pub async fn config_export(settings: State<'_, SharedSettings>) -> Result<String, String> {
+    let mut s = settings.read().await.without_secrets();
+    s.onboarding = Default::default();
+    s.to_toml_string()
+        .map_err(|e| format!("Failed to serialize config: {}", e))
+}
+
+/// Create the root, cache, environments, bin and state directories
+pub async fn ensure_data_dirs(settings: &SharedSettings) {
     let s = settings.read().await;
-    toml::to_string_pretty(&*s).map_err(|e| format!("Failed to serialize config: {}", e))
+    let dirs = [
+        s.get_root_dir(),
+        s.get_cache_dir(),
+        s.get_environments_dir(),
+        s.get_bin_dir(),
+        s.get_state_dir(),
+    ];
+    drop(s);
+    for dir in &dirs {
+        if let Err(e) = crate::platform::fs::create_dir_all(dir).await {
+            log::info!("Directory ensure error {:?}: {}", dir, e);
+        }
+    }
+}
+
+/// Redo the startup initialization that depends on the changed keys, so
+/// imported paths and mirrors apply without a restart
+async fn reinitialize_for_changes(
+    app: &AppHandle,
+    changes: &[ConfigChange],
+    settings: &SharedSettings,
+    registry: &SharedRegistry,
+) -> Result<(), String> {
+    if changes.iter().any(|c| config_key_moves_data_dirs(&c.key)) {
+        ensure_data_dirs(settings).await;
+        let download_manager = app.state::<SharedDownloadManager>().inner().clone();
+        download_manager.read().await.shutdown().await;
+        let s = settings.read().await;
+        setup_download_manager(download_manager, app.clone(), &s).await;
+    }
+    if changes.iter().any(|c| config_key_rebuilds_registry(&c.key)) {
+        refresh_provider_registry(settings, registry).await?;
+        invalidate_package_caches(settings).await;
+    }
+    Ok(())
 }
 
-/// Import a full backend config from a TOML string, replacing all settings
+/// Compare a TOML config against the current settings without applying it.
+/// Secret values are masked; keys that re-initialize providers or data
+/// directories are flagged.
+#[tauri::command]
+pub async fn config_import_preview(
+    toml_content: String,
+    settings: State<'_, SharedSettings>,
+) -> Result<ConfigImportPreview, String> {
+    let (mut incoming, report) =
+        Settings::parse_versioned(&toml_content).map_err(|e| e.to_string())?;
+    let s = settings.read().await;
+    incoming.onboarding = s.onboarding.clone();
+    let changes = s.diff(&incoming).map_err(|e| e.to_string())?;
+    Ok(ConfigImportPreview { changes, report })
+}
+
+/// Import a backend config from a TOML string. With `keys`, only those keys
+/// (as listed by `config_import_preview`) are applied; otherwise all settings
+/// are replaced. Older exports are migrated; values that do not fit are reset
+/// and reported. Plaintext tokens in older exports are moved into the secret
+/// store instead of being saved with the settings.
 #[tauri::command]
 pub async fn config_import(
     toml_content: String,
+    keys: Option<Vec<String>>,
+    app: AppHandle,
     settings: State<'_, SharedSettings>,
+    vault: State<'_, crate::SharedSecretVault>,
+    registry: State<'_, SharedRegistry>,
+) -> Result<SettingsLoadReport, String> {
+    let (parsed, report) = Settings::parse_versioned(&toml_content).map_err(|e| e.to_string())?;
+    let (mut next, report) = match keys {
+        Some(keys) => settings
+            .read()
+            .await
+            .apply_keys(&parsed, &keys)
+            .map_err(|e| e.to_string())?,
+        None => (parsed, report),
+    };
+    {
+        let current = settings.read().await;
+        next.onboarding = current.onboarding.clone();
+        // Imported smoke tests never arrive pre-approved
+        crate::core::smoke_test::retain_approvals(
+            &mut next.general.smoke_tests,
+            &current.general.smoke_tests,
+        );
+    }
+    crate::secrets::migrate_provider_secrets(
+        &mut next,
+        &*vault.read().await,
+        crate::platform::secrets::global(),
+    )
+    .await?;
+    replace_settings(&app, settings.inner(), registry.inner(), next).await?;
+    Ok(report)
+}
+
+/// Swap in `next` as the live settings, save it and redo the runtime setup
+/// that depends on the values that changed
+pub(crate) async fn replace_settings(
+    app: &AppHandle,
+    settings: &SharedSettings,
+    registry: &SharedRegistry,
+    next: Settings,
 ) -> Result<(), String> {
-    let parsed: crate::config::Settings =
-        toml::from_str(&toml_content).map_err(|e| format!("Failed to parse config: {}", e))?;
     let mut s = settings.write().await;
-    *s = parsed;
+    let changes = s.diff(&next).map_err(|e| e.to_string())?;
+    *s = next;
     s.save().await.map_err(|e| e.to_string())?;
     refresh_network_clients(&s);
-    Ok(())
+    refresh_asset_rules(&s);
+    refresh_extract_symlink_mode(&s);
+    drop(s);
+    apply_metrics_listener(app).await;
+    apply_local_api_server(app).await;
+    reinitialize_for_changes(app, &changes, settings, registry).await
+}
+
+/// Sign the chosen sections of the local configuration into a team config
+/// bundle (JSON) that `team_config_apply` accepts on other machines
+#[tauri::command]
+pub async fn team_config_export(
+    keys: Vec<TeamConfigSection>,
+    passphrase: String,
+    settings: State<'_, SharedSettings>,
+    detection: State<'_, SharedCustomDetectionManager>,
+) -> Result<String, String> {
+    let payload = {
+        let s = settings.read().await;
+        let detection = detection.read().await;
+        team_config::build_payload(&s, detection.list_rules(), &keys).map_err(|e| e.to_string())?
+    };
+    let bundle = team_config::seal(&payload, &passphrase).map_err(|e| e.to_string())?;
+    serde_json::to_string_pretty(&bundle).map_err(|e| e.to_string())
+}
+
+/// Verify a bundle and compute what applying it changes
+async fn open_team_config(
+    bundle: &str,
+    passphrase: &str,
+    settings: &SharedSettings,
+    detection: &SharedCustomDetectionManager,
+) -> Result<(TeamConfigPayload, String, Settings, TeamConfigPreview), String> {
+    let (payload, bundle_hash) =
+        team_config::open(bundle, passphrase).map_err(|e| e.to_string())?;
+    let s = settings.read().await;
+    let (incoming, report) =
+        team_config::incoming_settings(&s, &payload).map_err(|e| e.to_string())?;
+    let mut changes = s.diff(&incoming).map_err(|e| e.to_string())?;
+    changes.extend(team_config::rule_changes(
+        detection.read().await.list_rules(),
+        &payload,
+    ));
+    let preview = TeamConfigPreview {
+        version: payload.version,
+        created_at: payload.created_at.clone(),
+        sections: payload.sections.clone(),
+        changes,
+        report,
+    };
+    Ok((payload, bundle_hash, incoming, preview))
+}
+
+/// Verify a team config bundle and list what applying it would change
+#[tauri::command]
+pub async fn team_config_preview(
+    bundle: String,
+    passphrase: String,
+    settings: State<'_, SharedSettings>,
+    detection: State<'_, SharedCustomDetectionManager>,
+) -> Result<TeamConfigPreview, String> {
+    let (_, _, _, preview) =
+        open_team_config(&bundle, &passphrase, settings.inner(), detection.inner()).await?;
+    Ok(preview)
+}
+
+/// Verify and apply a team config bundle. Bundled detection rules are added
+/// or updated; local rules the bundle does not carry are kept. The applied
+/// state is recorded for `team_config_status`.
+#[tauri::command]
+pub async fn team_config_apply(
+    bundle: String,
+    passphrase: String,
+    app: AppHandle,
+    settings: State<'_, SharedSettings>,
+    registry: State<'_, SharedRegistry>,
+    detection: State<'_, SharedCustomDetectionManager>,
+) -> Result<TeamConfigPreview, String> {
+    let (payload, bundle_hash, incoming, preview) =
+        open_team_config(&bundle, &passphrase, settings.inner(), detection.inner()).await?;
+
+    if preview
+        .changes
+        .iter()
+        .any(|change| !change.key.starts_with(team_config::RULE_KEY_PREFIX))
+    {
+        replace_settings(&app, settings.inner(), registry.inner(), incoming).await?;
+    }
+
+    let mut detection = detection.write().await;
+    if !payload.detection_rules.is_empty() {
+        let now = chrono::Utc::now().to_rfc3339();
+        for rule in &payload.detection_rules {
+            let mut rule = rule.clone();
+            if detection.get_rule(&rule.id).is_some() {
+                rule.updated_at = Some(now.clone());
+                detection.update_rule(rule).map_err(|e| e.to_string())?;
+            } else {
+                rule.created_at = Some(now.clone());
+                detection.add_rule(rule).map_err(|e| e.to_string())?;
+            }
+        }
+        detection.save().await.map_err(|e| e.to_string())?;
+    }
+    let rules = detection.list_rules().to_vec();
+    drop(detection);
+
+    let s = settings.read().await;
+    let state =
+        team_config::applied_state(&payload, bundle_hash, &s, &rules).map_err(|e| e.to_string())?;
+    team_config::save_state(&s.get_state_dir(), &state)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(preview)
+}
+
+/// Whether the local configuration drifted from the last applied team
+/// config bundle
+#[tauri::command]
+pub async fn team_config_status(
+    settings: State<'_, SharedSettings>,
+    detection: State<'_, SharedCustomDetectionManager>,
+) -> Result<TeamConfigStatus, String> {
+    let s = settings.read().await;
+    let state = team_config::load_state(&s.get_state_dir())
+        .await
+        .map_err(|e| e.to_string())?;
+    team_config::status(state.as_ref(), &s, detection.read().await.list_rules())
+        .map_err(|e| e.to_string())
 }
 
 // ---------------------------------------------------------------------------
@@ -1456,67 +1826,134 @@ pub struct ProxyTestResult {
     pub success: bool,
     pub latency_ms: u64,
     pub error: Option<String>,
+    /// Proxy resolved for the requested provider, when one was given
+    pub effective_proxy: Option<EffectiveProxy>,
+}
+
+impl ProxyTestResult {
+    fn failed(error: String) -> Self {
+        Self {
+            success: false,
+            latency_ms: 0,
+            error: Some(error),
+            effective_proxy: None,
+        }
+    }
+}
+
+/// Endpoint used to test a provider's proxy when no test URL is given.
+fn default_proxy_test_url(provider: Option<&str>) -> &'static str {
+    match provider.map(|p| p.trim().to_lowercase()).as_deref() {
+        Some("github") => "https://api.github.com",
+        Some("gitlab") => "https://gitlab.com",
+        Some("npm" | "pnpm" | "yarn" | "bun") => "https://registry.npmjs.org",
+        Some("pypi" | "pip" | "pipx" | "uv" | "poetry") => "https://pypi.org/simple/",
+        Some("crates" | "cargo") => "https://index.crates.io/config.json",
+        _ => "https://www.google.com",
+    }
 }
 
 /// Test proxy connectivity by making a HEAD request through the given proxy.
+///
+/// When `provider` is set, the request goes through that provider's effective
+/// proxy (its override, else the global proxy). A non-empty `proxy_url` is then
+/// tested as a candidate override before it is saved.
 #[tauri::command]
 pub async fn test_proxy_connection(
-    proxy_url: String,
+    proxy_url: Option<String>,
     test_url: Option<String>,
+    provider: Option<String>,
+    settings: State<'_, SharedSettings>,
 ) -> Result<ProxyTestResult, String> {
-    let target = test_url.unwrap_or_else(|| "https://www.google.com".to_string());
-
-    let proxy = match reqwest::Proxy::all(&proxy_url) {
-        Ok(p) => p,
-        Err(e) => {
-            return Ok(ProxyTestResult {
-                success: false,
-                latency_ms: 0,
-                error: Some(format!("Invalid proxy URL: {}", e)),
-            });
+    let proxy_url = proxy_url
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty());
+    let provider = provider
+        .map(|p| p.trim().to_lowercase())
+        .filter(|p| !p.is_empty());
+    let target = test_url
+        .filter(|url| !url.trim().is_empty())
+        .unwrap_or_else(|| default_proxy_test_url(provider.as_deref()).to_string());
+
+    if let Some(url) = proxy_url.as_deref() {
+        let is_direct =
+            provider.is_some() && url.eq_ignore_ascii_case(crate::platform::proxy::DIRECT_PROXY);
+        if !is_direct && crate::platform::proxy::build_proxy(Some(url), None).is_none() {
+            return Ok(ProxyTestResult::failed(format!(
+                "Invalid proxy URL: {} (supported: http://, https://, socks5://)",
+                url
+            )));
         }
+    }
+
+    let Some(provider) = provider else {
+        let Some(url) = proxy_url else {
+            return Ok(ProxyTestResult::failed("Proxy URL is required".into()));
+        };
+        let client = match reqwest::Proxy::all(&url).and_then(|proxy| {
+            reqwest::Client::builder()
+                .proxy(proxy)
+                .timeout(std::time::Duration::from_secs(10))
+                .build()
+        }) {
+            Ok(c) => c,
+            Err(e) => {
+                return Ok(ProxyTestResult::failed(format!(
+                    "Failed to create test client: {}",
+                    e
+                )));
+            }
+        };
+        return Ok(send_proxy_test(&client, &target).await);
     };
 
-    let client = match reqwest::Client::builder()
-        .proxy(proxy)
-        .timeout(std::time::Duration::from_secs(10))
-        .build()
-    {
-        Ok(c) => c,
-        Err(e) => {
-            return Ok(ProxyTestResult {
-                success: false,
-                latency_ms: 0,
-                error: Some(format!("Failed to create test client: {}", e)),
-            });
+    let (client, effective) = {
+        let mut s = settings.read().await.clone();
+        if let Some(url) = proxy_url {
+            s.network.provider_proxies.insert(provider.clone(), url);
         }
+        // Keep the probe short regardless of the configured request timeout
+        s.network.timeout = s.network.timeout.min(10);
+        (
+            crate::platform::proxy::build_client_for_provider(&s, &provider),
+            crate::platform::proxy::effective_proxy(&s, Some(provider.as_str())),
+        )
     };
 
+    let mut result = send_proxy_test(&client, &target).await;
+    result.effective_proxy = Some(effective);
+    Ok(result)
+}
+
+async fn send_proxy_test(client: &reqwest::Client, target: &str) -> ProxyTestResult {
     let start = std::time::Instant::now();
-    match client.head(&target).send().await {
+    match client.head(target).send().await {
         Ok(resp) => {
             let latency = start.elapsed().as_millis() as u64;
             if resp.status().is_success() || resp.status().is_redirection() {
-                Ok(ProxyTestResult {
+                ProxyTestResult {
                     success: true,
                     latency_ms: latency,
                     error: None,
-                })
+                    effective_proxy: None,
+                }
             } else {
-                Ok(ProxyTestResult {
+                ProxyTestResult {
                     success: false,
                     latency_ms: latency,
                     error: Some(format!("HTTP {}", resp.status())),
-                })
+                    effective_proxy: None,
+                }
             }
         }
         Err(e) => {
             let latency = start.elapsed().as_millis() as u64;
-            Ok(ProxyTestResult {
+            ProxyTestResult {
                 success: false,
                 latency_ms: latency,
                 error: Some(e.to_string()),
-            })
+                effective_proxy: None,
+            }
         }
     }
 }
@@ -0,0 +1,26 @@
+use crate::provider::cuda::{self, CudaCompatibility, CudaToolkit};
+
+/// List the CUDA toolkits installed on this machine, newest first
+#[tauri::command]
+pub async fn cuda_list_installed() -> Result<Vec<CudaToolkit>, String> {
+    Ok(cuda::list_installed_toolkits().await)
+}
+
+/// Check whether the installed NVIDIA driver can run `requested_version`
+#[tauri::command]
+pub async fn cuda_detect_compatibility(
+    requested_version: String,
+) -> Result<CudaCompatibility, String> {
+    if cuda::release_of(&requested_version).is_none() {
+        return Err(format!(
+            "'{}' is not a valid CUDA version",
+            requested_version
+        ));
+    }
+    let driver = cuda::detect_driver().await;
+    Ok(cuda::check_compatibility(
+        &requested_version,
+        driver.as_ref(),
+        cfg!(windows),
+    ))
+}
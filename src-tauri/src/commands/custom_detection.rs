@@ -1,6 +1,9 @@
+use crate::commands::config::SharedSettings;
 use crate::core::custom_detection::{
-    create_preset_rules, CustomDetectionManager, CustomDetectionResult, CustomDetectionRule,
+    create_preset_rules, CustomDetectionManager, CustomDetectionReport, CustomDetectionResult,
+    CustomDetectionRule,
 };
+use crate::core::trash::TrashKind;
 use crate::error::CogniaResult;
 use std::sync::Arc;
 use tauri::State;
@@ -58,15 +61,35 @@ pub async fn custom_rule_update(
     manager.save().await.map_err(|e| e.to_string())
 }
 
-/// Delete a custom detection rule
+/// Delete a custom detection rule, keeping a copy in the trash
 #[tauri::command]
 pub async fn custom_rule_delete(
     rule_id: String,
     manager: State<'_, SharedCustomDetectionManager>,
+    settings: State<'_, SharedSettings>,
 ) -> Result<(), String> {
+    let root_dir = settings.read().await.get_root_dir();
     let mut manager = manager.write().await;
-    manager.delete_rule(&rule_id).map_err(|e| e.to_string())?;
-    manager.save().await.map_err(|e| e.to_string())
+    let rule = manager
+        .get_rule(&rule_id)
+        .cloned()
+        .ok_or_else(|| format!("Rule '{}' not found", rule_id))?;
+    let entry = super::trash::trash_before_delete(
+        &root_dir,
+        TrashKind::CustomRule,
+        &rule.id,
+        &rule.name,
+        &rule,
+    )
+    .await?;
+    let result = match manager.delete_rule(&rule_id) {
+        Ok(()) => manager.save().await.map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+    if result.is_err() {
+        super::trash::discard(&root_dir, &entry).await;
+    }
+    result
 }
 
 /// Enable or disable a custom detection rule
@@ -129,10 +152,9 @@ pub async fn custom_rule_detect(
     manager: State<'_, SharedCustomDetectionManager>,
 ) -> Result<Option<CustomDetectionResult>, String> {
     let manager = manager.read().await;
-    manager
+    Ok(manager
         .detect(&env_type, std::path::Path::new(&start_path))
-        .await
-        .map_err(|e| e.to_string())
+        .await)
 }
 
 /// Detect all versions using custom rules
@@ -140,27 +162,32 @@ pub async fn custom_rule_detect(
 pub async fn custom_rule_detect_all(
     start_path: String,
     manager: State<'_, SharedCustomDetectionManager>,
-) -> Result<Vec<CustomDetectionResult>, String> {
+) -> Result<CustomDetectionReport, String> {
     let manager = manager.read().await;
-    manager
-        .detect_all(std::path::Path::new(&start_path))
-        .await
-        .map_err(|e| e.to_string())
+    Ok(manager.detect_all(std::path::Path::new(&start_path)).await)
 }
 
 /// Test a rule against a specific file (for validation before saving)
+///
+/// Command-based rules only run when the command line is an allowlisted version query,
+/// the rule carries a matching approval, or the caller passes `approve_command` after user confirmation.
 #[tauri::command]
 pub async fn custom_rule_test(
     rule: CustomDetectionRule,
     test_path: String,
+    approve_command: Option<bool>,
 ) -> Result<TestRuleResult, String> {
+    // Create a temporary manager just for testing
     let manager = CustomDetectionManager::new(std::path::Path::new(""));
+    let mut rule = rule;
+    if approve_command.unwrap_or(false) {
+        rule.approved_command = rule.command_signature();
+    }
 
-    // Create a temporary manager just for testing
     let start = std::time::Instant::now();
 
     match manager
-        .detect(&rule.env_type, std::path::Path::new(&test_path))
+        .test_rule(&rule, std::path::Path::new(&test_path))
         .await
     {
         Ok(Some(result)) => Ok(TestRuleResult {
@@ -190,6 +217,19 @@ pub async fn custom_rule_test(
     }
 }
 
+/// Approve the command a rule runs, allowing command lines off the allowlist to execute
+#[tauri::command]
+pub async fn custom_rule_approve_command(
+    rule_id: String,
+    manager: State<'_, SharedCustomDetectionManager>,
+) -> Result<(), String> {
+    let mut manager = manager.write().await;
+    manager
+        .approve_command(&rule_id)
+        .map_err(|e| e.to_string())?;
+    manager.save().await.map_err(|e| e.to_string())
+}
+
 /// Validate a regex pattern
 #[tauri::command]
 pub async fn custom_rule_validate_regex(pattern: String) -> Result<RegexValidation, String> {
@@ -341,6 +381,12 @@ pub async fn custom_rule_extraction_types() -> Result<Vec<ExtractionTypeInfo>, S
             description: "Run a command and extract version from output".to_string(),
             example: r#"{"type": "command", "cmd": "node", "args": ["--version"], "output_pattern": "v(?P<version>[\\d.]+)"}"#.to_string(),
         },
+        ExtractionTypeInfo {
+            type_name: "command_output".to_string(),
+            display_name: "Command Output (JSON/Regex)".to_string(),
+            description: "Run a command with a timeout and extract the version from stdout via JSON pointer or regex; commands other than allowlisted version queries require approval".to_string(),
+            example: r#"{"type": "command_output", "command": "terraform", "args": ["version", "-json"], "json_pointer": "/terraform_version"}"#.to_string(),
+        },
     ])
 }
 
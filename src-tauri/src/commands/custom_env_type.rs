@@ -0,0 +1,171 @@
+use crate::commands::config::SharedSettings;
+use crate::commands::custom_detection::SharedCustomDetectionManager;
+use crate::core::custom_env_type::{CustomEnvTypeDefinition, CustomEnvTypeManager};
+use crate::error::CogniaResult;
+use crate::provider::custom_env::CustomEnvProvider;
+use crate::provider::SharedRegistry;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::RwLock;
+
+pub type SharedCustomEnvTypeManager = Arc<RwLock<CustomEnvTypeManager>>;
+
+/// Create a shared custom environment type manager
+pub async fn create_shared_custom_env_type_manager(
+    config_dir: &std::path::Path,
+) -> CogniaResult<SharedCustomEnvTypeManager> {
+    let mut manager = CustomEnvTypeManager::new(config_dir);
+    manager.load().await?;
+    Ok(Arc::new(RwLock::new(manager)))
+}
+
+/// Register one provider per custom environment type, replacing the previous set
+pub async fn sync_custom_env_providers(
+    manager: &SharedCustomEnvTypeManager,
+    detection: &SharedCustomDetectionManager,
+    registry: &SharedRegistry,
+    settings: &SharedSettings,
+) {
+    let root_dir = settings.read().await.get_root_dir();
+    let providers = manager
+        .read()
+        .await
+        .list()
+        .iter()
+        .map(|definition| {
+            Arc::new(CustomEnvProvider::new(
+                definition.clone(),
+                &root_dir,
+                detection.clone(),
+            ))
+        })
+        .collect();
+    registry
+        .write()
+        .await
+        .set_custom_environment_providers(providers);
+}
+
+async fn detection_rule_ids(detection: &SharedCustomDetectionManager) -> Vec<String> {
+    detection
+        .read()
+        .await
+        .list_rules()
+        .iter()
+        .map(|rule| rule.id.clone())
+        .collect()
+}
+
+/// List all custom environment types
+#[tauri::command]
+pub async fn custom_env_type_list(
+    manager: State<'_, SharedCustomEnvTypeManager>,
+) -> Result<Vec<CustomEnvTypeDefinition>, String> {
+    Ok(manager.read().await.list().to_vec())
+}
+
+/// Get a custom environment type by ID
+#[tauri::command]
+pub async fn custom_env_type_get(
+    id: String,
+    manager: State<'_, SharedCustomEnvTypeManager>,
+) -> Result<Option<CustomEnvTypeDefinition>, String> {
+    Ok(manager.read().await.get(&id).cloned())
+}
+
+/// Problems that would prevent saving a definition (empty when valid)
+#[tauri::command]
+pub async fn custom_env_type_validate(
+    definition: CustomEnvTypeDefinition,
+    detection: State<'_, SharedCustomDetectionManager>,
+) -> Result<Vec<String>, String> {
+    let rule_ids = detection_rule_ids(detection.inner()).await;
+    let rule_ids: Vec<&str> = rule_ids.iter().map(String::as_str).collect();
+    Ok(definition.validate(&rule_ids))
+}
+
+/// Add a custom environment type and register its provider
+#[tauri::command]
+pub async fn custom_env_type_add(
+    definition: CustomEnvTypeDefinition,
+    manager: State<'_, SharedCustomEnvTypeManager>,
+    detection: State<'_, SharedCustomDetectionManager>,
+    registry: State<'_, SharedRegistry>,
+    settings: State<'_, SharedSettings>,
+) -> Result<(), String> {
+    let rule_ids = detection_rule_ids(detection.inner()).await;
+    let rule_ids: Vec<&str> = rule_ids.iter().map(String::as_str).collect();
+    let mut definition = definition;
+    definition.created_at = Some(chrono::Utc::now().to_rfc3339());
+    {
+        let mut manager = manager.write().await;
+        manager
+            .add(definition, &rule_ids)
+            .map_err(|e| e.to_string())?;
+        manager.save().await.map_err(|e| e.to_string())?;
+    }
+    sync_custom_env_providers(&manager, &detection, &registry, &settings).await;
+    Ok(())
+}
+
+/// Update a custom environment type. Approvals survive only for commands
+/// that did not change.
+#[tauri::command]
+pub async fn custom_env_type_update(
+    definition: CustomEnvTypeDefinition,
+    manager: State<'_, SharedCustomEnvTypeManager>,
+    detection: State<'_, SharedCustomDetectionManager>,
+    registry: State<'_, SharedRegistry>,
+    settings: State<'_, SharedSettings>,
+) -> Result<(), String> {
+    let rule_ids = detection_rule_ids(detection.inner()).await;
+    let rule_ids: Vec<&str> = rule_ids.iter().map(String::as_str).collect();
+    let mut definition = definition;
+    definition.updated_at = Some(chrono::Utc::now().to_rfc3339());
+    {
+        let mut manager = manager.write().await;
+        manager
+            .update(definition, &rule_ids)
+            .map_err(|e| e.to_string())?;
+        manager.save().await.map_err(|e| e.to_string())?;
+    }
+    sync_custom_env_providers(&manager, &detection, &registry, &settings).await;
+    Ok(())
+}
+
+/// Delete a custom environment type. Installed versions stay on disk.
+#[tauri::command]
+pub async fn custom_env_type_delete(
+    id: String,
+    manager: State<'_, SharedCustomEnvTypeManager>,
+    detection: State<'_, SharedCustomDetectionManager>,
+    registry: State<'_, SharedRegistry>,
+    settings: State<'_, SharedSettings>,
+) -> Result<(), String> {
+    {
+        let mut manager = manager.write().await;
+        manager.delete(&id).map_err(|e| e.to_string())?;
+        manager.save().await.map_err(|e| e.to_string())?;
+    }
+    sync_custom_env_providers(&manager, &detection, &registry, &settings).await;
+    Ok(())
+}
+
+/// Approve every command a custom environment type runs, allowing
+/// non-allowlisted programs to execute
+#[tauri::command]
+pub async fn custom_env_type_approve_commands(
+    id: String,
+    manager: State<'_, SharedCustomEnvTypeManager>,
+    detection: State<'_, SharedCustomDetectionManager>,
+    registry: State<'_, SharedRegistry>,
+    settings: State<'_, SharedSettings>,
+) -> Result<(), String> {
+    {
+        let mut manager = manager.write().await;
+        manager.approve_commands(&id).map_err(|e| e.to_string())?;
+        manager.save().await.map_err(|e| e.to_string())?;
+    }
+    sync_custom_env_providers(&manager, &detection, &registry, &settings).await;
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use crate::core::deep_link::{self, DeepLinkRequest};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
+
+/// Links received but not yet picked up by the frontend. Links that arrive
+/// while the webview is still loading (e.g. the one the app was launched
+/// with) wait here.
+pub type SharedPendingDeepLinks = Arc<Mutex<Vec<DeepLinkRequest>>>;
+
+/// Validate incoming links and queue them for confirmation in the UI.
+/// Nothing is executed here; rejected links are logged with the reason.
+pub fn handle_urls<R: Runtime>(app: &AppHandle<R>, urls: impl IntoIterator<Item = String>) {
+    let accepted: Vec<DeepLinkRequest> = urls
+        .into_iter()
+        .filter_map(|url| match deep_link::parse(&url) {
+            Ok(request) => {
+                log::info!("Received deep link {}", request.url);
+                Some(request)
+            }
+            Err(e) => {
+                log::warn!("Rejected deep link '{}': {}", url, e);
+                None
+            }
+        })
+        .collect();
+    if accepted.is_empty() {
+        return;
+    }
+
+    if let Some(pending) = app.try_state::<SharedPendingDeepLinks>() {
+        if let Ok(mut guard) = pending.lock() {
+            guard.extend(accepted.iter().cloned());
+        }
+    }
+    for request in &accepted {
+        let _ = app.emit("deep-link-received", request);
+    }
+}
+
+/// Pick `cognia://` links out of command-line arguments, as passed on a cold
+/// start or forwarded by the single-instance callback.
+pub fn handle_args<R: Runtime>(app: &AppHandle<R>, args: &[String]) {
+    handle_urls(
+        app,
+        args.iter()
+            .filter(|arg| deep_link::is_deep_link(arg))
+            .cloned(),
+    );
+}
+
+/// Validate a link and describe its action for the confirmation dialog
+#[tauri::command]
+pub async fn deeplink_parse(url: String) -> Result<DeepLinkRequest, String> {
+    deep_link::parse(&url).map_err(|e| {
+        log::warn!("Rejected deep link '{}': {}", url, e);
+        e.to_string()
+    })
+}
+
+/// Take the links waiting for confirmation, oldest first
+#[tauri::command]
+pub async fn deeplink_take_pending(
+    pending: State<'_, SharedPendingDeepLinks>,
+) -> Result<Vec<DeepLinkRequest>, String> {
+    let mut guard = pending.lock().map_err(|e| e.to_string())?;
+    Ok(std::mem::take(&mut *guard))
+}
@@ -1140,20 +1140,10 @@ fn collect_safe_environment_json() -> String {
 }
 
 /// Sanitize a Settings snapshot for export — redact tokens and secrets.
+/// Provider tokens live in the secret store and are never read here; any
+/// plaintext token left in settings is dropped for its reference.
 fn sanitize_config_toml(settings: &crate::config::Settings) -> Option<String> {
-    // Clone + redact
-    let mut s = settings.clone();
-
-    // Redact provider tokens
-    for (_name, ps) in s.providers.iter_mut() {
-        if let Some(token) = ps.extra.get_mut("token") {
-            if let Some(t) = token.as_str() {
-                if !t.is_empty() {
-                    *token = toml::Value::String("***REDACTED***".into());
-                }
-            }
-        }
-    }
+    let mut s = settings.without_secrets();
 
     // Redact proxy (may contain credentials)
     if let Some(ref proxy) = s.network.proxy {
@@ -1359,6 +1349,7 @@ fn test_sanitize_config_toml() {
 
         let result = sanitize_config_toml(&settings).unwrap();
         assert!(result.contains("REDACTED"));
+        assert!(result.contains("secret_saved = true"));
         assert!(!result.contains("ghp_secret123"));
         assert!(!result.contains("user:pass"));
     }
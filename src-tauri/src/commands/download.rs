@@ -1,19 +1,24 @@
 //! Download management commands
 
 use crate::cache::download_history::{
-    DownloadHistory, DownloadRecord, DownloadStatus, HistoryStats,
+    DownloadHistory, DownloadRecord, DownloadStatus, DownloadUsageSample, DownloadUsageStats,
+    HistoryExportFormat, HistoryFilter, HistoryImportResult, HistoryRetention, HistoryStats,
+    UsagePeriod,
 };
+use crate::cache::{DownloadResumer, PartialValidation};
+use crate::commands::notifications::notify;
 use crate::config::Settings;
+use crate::core::notifications::{Notification, NotificationCategory, NotificationSeverity};
 use crate::download::{
-    DownloadConfig, DownloadEvent, DownloadManager, DownloadManagerConfig, DownloadState,
-    DownloadTask, ShutdownOutcome,
+    coalesce_progress_events, DownloadConfig, DownloadEvent, DownloadManager,
+    DownloadManagerConfig, DownloadProgress, DownloadState, DownloadTask, ShutdownOutcome,
 };
 use crate::platform::disk::{self, format_size, DiskSpace};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::sync::RwLock;
 
 /// Shared download manager state
@@ -70,6 +75,24 @@ pub struct DownloadProgressInfo {
     pub eta_human: Option<String>,
     pub downloaded_human: String,
     pub total_human: Option<String>,
+    pub stalled: bool,
+}
+
+impl From<&DownloadProgress> for DownloadProgressInfo {
+    fn from(progress: &DownloadProgress) -> Self {
+        Self {
+            downloaded_bytes: progress.downloaded_bytes,
+            total_bytes: progress.total_bytes,
+            speed: progress.speed,
+            speed_human: progress.speed_human(),
+            percent: progress.percent,
+            eta_secs: progress.eta_secs,
+            eta_human: progress.eta_human(),
+            downloaded_human: progress.downloaded_human(),
+            total_human: progress.total_human(),
+            stalled: progress.stalled,
+        }
+    }
 }
 
 impl From<&DownloadTask> for DownloadTaskInfo {
@@ -80,17 +103,7 @@ fn from(task: &DownloadTask) -> Self {
             name: task.name.clone(),
             destination: task.destination.display().to_string(),
             state: task.state.status_text().to_string(),
-            progress: DownloadProgressInfo {
-                downloaded_bytes: task.progress.downloaded_bytes,
-                total_bytes: task.progress.total_bytes,
-                speed: task.progress.speed,
-                speed_human: task.progress.speed_human(),
-                percent: task.progress.percent,
-                eta_secs: task.progress.eta_secs,
-                eta_human: task.progress.eta_human(),
-                downloaded_human: task.progress.downloaded_human(),
-                total_human: task.progress.total_human(),
-            },
+            progress: DownloadProgressInfo::from(&task.progress),
             error: task.error.clone(),
             provider: task.provider.clone(),
             created_at: task.created_at.to_rfc3339(),
@@ -183,6 +196,24 @@ pub struct DownloadRequest {
     pub source_descriptor: Option<crate::download::SourceDescriptor>,
     #[serde(default)]
     pub artifact_profile: Option<crate::download::ArtifactProfile>,
+    /// Environment version that consumes this artifact; protects its cache entry from eviction
+    #[serde(default)]
+    pub env_reference: Option<DownloadEnvReference>,
+    /// Probe `<url>.sha256` when no checksum is given (default off; the probe
+    /// delays adding the task by up to the sidecar timeout)
+    #[serde(default)]
+    pub discover_checksum: Option<bool>,
+    /// Extra task metadata, e.g. where an attached checksum came from
+    #[serde(default)]
+    pub metadata: Option<std::collections::HashMap<String, String>>,
+}
+
+/// Environment version an artifact is downloaded for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadEnvReference {
+    pub env_type: String,
+    pub version: String,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -232,6 +263,47 @@ pub(crate) fn build_download_request_preset(
         install_intent: preset.install_intent,
         source_descriptor: preset.source_descriptor,
         artifact_profile: preset.artifact_profile,
+        env_reference: None,
+        discover_checksum: None,
+        metadata: None,
+    }
+}
+
+/// Attach an auto-discovered checksum, and where it came from, to `request`
+pub(crate) fn apply_checksum_discovery(
+    request: &mut DownloadRequest,
+    discovery: crate::download::checksum_discovery::ChecksumDiscovery,
+) {
+    let metadata = discovery.metadata();
+    if let Some(checksum) = discovery.checksum {
+        request.checksum = Some(checksum);
+    }
+    if !metadata.is_empty() {
+        request
+            .metadata
+            .get_or_insert_with(Default::default)
+            .extend(metadata);
+    }
+}
+
+/// Record that `env_type@version` consumes the cached artifact with `checksum`.
+async fn record_cache_reference(
+    dl_cache: &mut crate::cache::DownloadCache,
+    checksum: &str,
+    env_type: &str,
+    version: &str,
+) {
+    if env_type.is_empty() || version.is_empty() {
+        return;
+    }
+    if let Err(error) = dl_cache.add_reference(checksum, env_type, version).await {
+        log::warn!(
+            "Failed to record cache reference {}@{} -> {}: {}",
+            env_type,
+            version,
+            checksum,
+            error
+        );
     }
 }
 
@@ -251,22 +323,31 @@ pub async fn setup_download_manager(
         default_task_config: DownloadConfig::default(),
         partials_dir: settings.get_cache_dir().join("partials"),
         auto_start: true,
-        progress_interval_ms: 100,
+        progress_interval_ms: settings.general.progress_event_interval_ms,
+        ..Default::default()
     };
+    let progress_interval = std::time::Duration::from_millis(config.progress_interval_ms);
 
     let cache_dir = settings.get_cache_dir();
 
     let client = crate::platform::proxy::build_client(settings);
     let mut manager = DownloadManager::new(config, client);
-    let mut rx = manager.create_event_channel();
+    manager.enable_shared_clients();
+    manager.enable_shared_speed_limit();
+    let mut rx = coalesce_progress_events(manager.create_event_channel(), progress_interval);
 
     // Enable queue persistence for crash recovery
     manager.enable_persistence(&cache_dir);
 
     // Restore tasks from previous session
-    let restored = manager.load_persisted_tasks().await;
-    if restored > 0 {
-        log::info!("Restored {} download tasks from previous session", restored);
+    let restored = manager
+        .load_persisted_tasks(settings.general.download_auto_resume)
+        .await;
+    if restored.total() > 0 {
+        log::info!(
+            "Restored {} download tasks from previous session",
+            restored.total()
+        );
     }
 
     // Clean up stale partial downloads on startup (older than 7 days)
@@ -293,34 +374,32 @@ pub async fn setup_download_manager(
     let manager_clone = shared_manager.clone();
     let cache_dir_clone = cache_dir.clone();
     tokio::spawn(async move {
-        use tauri_plugin_notification::NotificationExt;
-
         while let Some(event) = rx.recv().await {
             // Emit events to frontend with enriched payloads where needed
             match &event {
-                DownloadEvent::TaskProgress { task_id, progress } => {
-                    // Enrich with human-readable fields the frontend expects
+                DownloadEvent::ProgressBatch { updates } => {
+                    // Enrich with human-readable fields the frontend expects.
+                    // One event per tick for all tasks, so the UI renders once.
                     // Note: task_id stays snake_case to match existing frontend event listeners
                     #[derive(Serialize)]
                     struct ProgressPayload {
                         task_id: String,
                         progress: DownloadProgressInfo,
                     }
-                    let payload = ProgressPayload {
-                        task_id: task_id.clone(),
-                        progress: DownloadProgressInfo {
-                            downloaded_bytes: progress.downloaded_bytes,
-                            total_bytes: progress.total_bytes,
-                            speed: progress.speed,
-                            speed_human: progress.speed_human(),
-                            percent: progress.percent,
-                            eta_secs: progress.eta_secs,
-                            eta_human: progress.eta_human(),
-                            downloaded_human: progress.downloaded_human(),
-                            total_human: progress.total_human(),
-                        },
+                    #[derive(Serialize)]
+                    struct BatchPayload {
+                        updates: Vec<ProgressPayload>,
+                    }
+                    let payload = BatchPayload {
+                        updates: updates
+                            .iter()
+                            .map(|update| ProgressPayload {
+                                task_id: update.task_id.clone(),
+                                progress: DownloadProgressInfo::from(&update.progress),
+                            })
+                            .collect(),
                     };
-                    let _ = app_clone.emit("download-task-progress", &payload);
+                    let _ = app_clone.emit("download-progress-batch", &payload);
                 }
                 DownloadEvent::QueueUpdated { stats } => {
                     // Enrich with human-readable fields
@@ -362,39 +441,63 @@ struct QueuePayload {
                         DownloadEvent::TaskPaused { .. } => "download-task-paused",
                         DownloadEvent::TaskResumed { .. } => "download-task-resumed",
                         DownloadEvent::TaskCancelled { .. } => "download-task-cancelled",
+                        DownloadEvent::TaskStalled { .. } => "download-task-stalled",
                         DownloadEvent::TaskExtracting { .. } => "download-task-extracting",
+                        DownloadEvent::TaskExtractProgress { .. } => {
+                            "download-task-extract-progress"
+                        }
                         DownloadEvent::TaskExtracted { .. } => "download-task-extracted",
+                        DownloadEvent::QueueRestored { .. } => "download-queue-restored",
                         _ => unreachable!(),
                     };
                     let _ = app_clone.emit(event_name, &event);
                 }
             }
 
-            // Send desktop notifications for completed/failed downloads
+            // Notify about completed/failed downloads
             match &event {
                 DownloadEvent::TaskCompleted { task_id } => {
                     let mgr = manager_clone.read().await;
-                    if let Some(task) = mgr.get_task(task_id).await {
-                        let _ = app_clone
-                            .notification()
-                            .builder()
-                            .title("Download Complete")
-                            .body(format!("{} has finished downloading.", task.name))
-                            .show();
-                    }
+                    let task = mgr.get_task(task_id).await;
                     drop(mgr);
+                    if let Some(task) = task {
+                        notify(
+                            &app_clone,
+                            Notification::new(
+                                NotificationCategory::Download,
+                                NotificationSeverity::Success,
+                                "Download Complete",
+                                format!("{} has finished downloading.", task.name),
+                            )
+                            .with_payload(serde_json::json!({
+                                "taskId": task_id,
+                                "name": task.name,
+                            })),
+                        )
+                        .await;
+                    }
                 }
                 DownloadEvent::TaskFailed { task_id, error, .. } => {
                     let mgr = manager_clone.read().await;
-                    if let Some(task) = mgr.get_task(task_id).await {
-                        let _ = app_clone
-                            .notification()
-                            .builder()
-                            .title("Download Failed")
-                            .body(format!("{}: {}", task.name, error))
-                            .show();
-                    }
+                    let task = mgr.get_task(task_id).await;
                     drop(mgr);
+                    if let Some(task) = task {
+                        notify(
+                            &app_clone,
+                            Notification::new(
+                                NotificationCategory::Download,
+                                NotificationSeverity::Error,
+                                "Download Failed",
+                                format!("{}: {}", task.name, error),
+                            )
+                            .with_payload(serde_json::json!({
+                                "taskId": task_id,
+                                "name": task.name,
+                                "error": error,
+                            })),
+                        )
+                        .await;
+                    }
                 }
                 _ => {}
             }
@@ -421,6 +524,10 @@ struct QueuePayload {
                         );
                         let dest = task.destination.clone();
                         let checksum = task.expected_checksum.clone();
+                        let usage = DownloadUsageSample::from_task(
+                            &task,
+                            mgr.take_received_bytes(task_id).await,
+                        );
                         drop(mgr);
 
                         // Record to history
@@ -428,6 +535,7 @@ struct QueuePayload {
                             if let Err(e) = history.add(record).await {
                                 log::warn!("Failed to record download history: {}", e);
                             }
+                            record_download_usage(&app_clone, &mut history, usage).await;
                         }
 
                         // Add to download cache for deduplication (only if checksum is available)
@@ -453,6 +561,21 @@ struct QueuePayload {
                                             checksum_val,
                                             cached_path
                                         );
+                                        if let (Some(env_type), Some(version)) = (
+                                            task.metadata
+                                                .get(crate::download::task::ENV_TYPE_METADATA_KEY),
+                                            task.metadata.get(
+                                                crate::download::task::ENV_VERSION_METADATA_KEY,
+                                            ),
+                                        ) {
+                                            record_cache_reference(
+                                                &mut dl_cache,
+                                                checksum_val,
+                                                env_type,
+                                                version,
+                                            )
+                                            .await;
+                                        }
                                     }
                                     Err(e) => {
                                         log::warn!("Failed to cache download: {}", e);
@@ -479,11 +602,16 @@ struct QueuePayload {
                             task.install_intent,
                             task.metadata.clone(),
                         );
+                        let usage = DownloadUsageSample::from_task(
+                            &task,
+                            mgr.take_received_bytes(task_id).await,
+                        );
                         drop(mgr);
                         if let Ok(mut history) = DownloadHistory::open(&cache_dir_clone).await {
                             if let Err(e) = history.add(record).await {
                                 log::warn!("Failed to record download history: {}", e);
                             }
+                            record_download_usage(&app_clone, &mut history, usage).await;
                         }
                     }
                 }
@@ -504,11 +632,16 @@ struct QueuePayload {
                             task.install_intent,
                             task.metadata.clone(),
                         );
+                        let usage = DownloadUsageSample::from_task(
+                            &task,
+                            mgr.take_received_bytes(task_id).await,
+                        );
                         drop(mgr);
                         if let Ok(mut history) = DownloadHistory::open(&cache_dir_clone).await {
                             if let Err(e) = history.add(record).await {
                                 log::warn!("Failed to record download history: {}", e);
                             }
+                            record_download_usage(&app_clone, &mut history, usage).await;
                         }
                     }
                 }
@@ -518,19 +651,77 @@ struct QueuePayload {
     });
 }
 
+/// Account a finished task's or cache hit's bytes and raise the monthly soft-cap
+/// notification the first time `general.download_monthly_cap_mb` is exceeded in a month
+async fn record_download_usage(
+    app: &AppHandle,
+    history: &mut DownloadHistory,
+    sample: DownloadUsageSample,
+) {
+    if let Err(e) = history.record_usage(sample).await {
+        log::warn!("Failed to record bandwidth usage: {}", e);
+        return;
+    }
+
+    let cap_mb = match app.try_state::<SharedSettings>() {
+        Some(settings) => settings.read().await.general.download_monthly_cap_mb,
+        None => return,
+    };
+    match history
+        .take_cap_exceeded(cap_mb.saturating_mul(1024 * 1024), chrono::Utc::now())
+        .await
+    {
+        Ok(Some(used)) => {
+            let cap = cap_mb.saturating_mul(1024 * 1024);
+            notify(
+                app,
+                Notification::new(
+                    NotificationCategory::Download,
+                    NotificationSeverity::Warning,
+                    "Monthly download cap exceeded",
+                    format!(
+                        "{} downloaded this month (cap: {}).",
+                        format_size(used),
+                        format_size(cap)
+                    ),
+                )
+                .with_payload(serde_json::json!({ "usedBytes": used, "capBytes": cap })),
+            )
+            .await;
+        }
+        Ok(None) => {}
+        Err(e) => log::warn!("Failed to check monthly download cap: {}", e),
+    }
+}
+
 /// Add a new download task
 ///
 /// If the request includes a checksum and the file already exists in the download cache,
 /// the cached file is copied to the destination directly (cache-hit shortcut).
 /// Returns the task ID on normal download, or `"cache-hit:<checksum>"` if served from cache.
+/// Without a checksum and with `discover_checksum` set, a conventional `<url>.sha256`
+/// sidecar is probed first so the download is still verified on completion.
 #[tauri::command]
 pub async fn download_add(
+    app: AppHandle,
     request: DownloadRequest,
     manager: State<'_, SharedDownloadManager>,
     settings: State<'_, SharedSettings>,
 ) -> Result<String, String> {
+    let mut request = request;
     let destination = PathBuf::from(&request.destination);
 
+    let has_checksum = request.checksum.as_ref().is_some_and(|c| !c.is_empty());
+    if !has_checksum && request.discover_checksum.unwrap_or(false) {
+        let discovery = crate::download::checksum_discovery::probe_conventional(
+            &request.url,
+            &request.name,
+            &request.headers.clone().unwrap_or_default(),
+        )
+        .await;
+        apply_checksum_discovery(&mut request, discovery);
+    }
+
     // Cache-hit shortcut: if checksum provided, check if already cached
     if let Some(ref checksum) = request.checksum {
         if !checksum.is_empty() {
@@ -572,7 +763,7 @@ pub async fn download_add(
                                         request.source_descriptor.clone(),
                                         request.artifact_profile.clone(),
                                         request.install_intent,
-                                        std::collections::HashMap::new(),
+                                        request.metadata.clone().unwrap_or_default(),
                                     );
                                     if let Err(error) = history.add(record).await {
                                         log::warn!(
@@ -580,6 +771,22 @@ pub async fn download_add(
                                             error
                                         );
                                     }
+                                    let usage = DownloadUsageSample::cache_hit(
+                                        &request.url,
+                                        request.provider.as_deref(),
+                                        size,
+                                    );
+                                    record_download_usage(&app, &mut history, usage).await;
+                                }
+
+                                if let Some(ref reference) = request.env_reference {
+                                    record_cache_reference(
+                                        &mut dl_cache,
+                                        checksum,
+                                        &reference.env_type,
+                                        &reference.version,
+                                    )
+                                    .await;
                                 }
 
                                 log::info!(
@@ -642,6 +849,20 @@ pub async fn download_add(
             builder = builder.with_mirrors(mirror_urls);
         }
     }
+    for (key, value) in request.metadata.unwrap_or_default() {
+        builder = builder.with_metadata(key, value);
+    }
+    if let Some(reference) = request.env_reference {
+        builder = builder
+            .with_metadata(
+                crate::download::task::ENV_TYPE_METADATA_KEY.to_string(),
+                reference.env_type,
+            )
+            .with_metadata(
+                crate::download::task::ENV_VERSION_METADATA_KEY.to_string(),
+                reference.version,
+            );
+    }
 
     let mut task = builder.build();
 
@@ -1014,6 +1235,30 @@ pub async fn download_calculate_checksum(path: String) -> Result<String, String>
         .map_err(|e| e.to_string())
 }
 
+/// Check partial downloads against their resume journals. With `repair`,
+/// inconsistent files are truncated to their last verified offset.
+#[tauri::command]
+pub async fn download_validate_partials(
+    repair: Option<bool>,
+    manager: State<'_, SharedDownloadManager>,
+    settings: State<'_, SharedSettings>,
+) -> Result<Vec<PartialValidation>, String> {
+    let repair = repair.unwrap_or(false);
+    let mut reports = manager.read().await.validate_partials(repair).await;
+
+    let cache_dir = settings.read().await.get_cache_dir();
+    let mut resumer = DownloadResumer::new(&cache_dir.join("downloads"))
+        .await
+        .map_err(|e| e.to_string())?;
+    reports.extend(
+        resumer
+            .validate_all(repair)
+            .await
+            .map_err(|e| e.to_string())?,
+    );
+    Ok(reports)
+}
+
 /// Batch pause selected downloads
 #[tauri::command]
 pub async fn download_batch_pause(
@@ -1255,6 +1500,84 @@ pub async fn download_history_clear(
     }
 }
 
+/// Export download history to a CSV or JSON file, returning the number of records written
+#[tauri::command]
+pub async fn download_history_export(
+    format: HistoryExportFormat,
+    path: String,
+    filter: Option<HistoryFilter>,
+    settings: State<'_, SharedSettings>,
+) -> Result<usize, String> {
+    let s = settings.read().await;
+    let cache_dir = s.get_cache_dir();
+    drop(s);
+
+    let history = DownloadHistory::open(&cache_dir)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    history
+        .export(format, &PathBuf::from(path), &filter.unwrap_or_default())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Restore download history from a previous CSV or JSON export
+#[tauri::command]
+pub async fn download_history_import(
+    path: String,
+    format: Option<HistoryExportFormat>,
+    settings: State<'_, SharedSettings>,
+) -> Result<HistoryImportResult, String> {
+    let s = settings.read().await;
+    let cache_dir = s.get_cache_dir();
+    let retention = HistoryRetention {
+        max_age_days: s.general.download_history_max_age_days,
+        max_records: s.general.download_history_max_records,
+    };
+    drop(s);
+
+    let mut history = DownloadHistory::open(&cache_dir)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    history
+        .import(&PathBuf::from(path), format, retention, chrono::Utc::now())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get bandwidth usage grouped by provider, day and host
+#[tauri::command]
+pub async fn download_usage_stats(
+    period: Option<UsagePeriod>,
+    settings: State<'_, SharedSettings>,
+) -> Result<DownloadUsageStats, String> {
+    let s = settings.read().await;
+    let cache_dir = s.get_cache_dir();
+    drop(s);
+
+    let history = DownloadHistory::open(&cache_dir)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(history.usage_stats(period.unwrap_or_default(), chrono::Utc::now()))
+}
+
+/// Reset bandwidth usage accounting
+#[tauri::command]
+pub async fn download_usage_reset(settings: State<'_, SharedSettings>) -> Result<usize, String> {
+    let s = settings.read().await;
+    let cache_dir = s.get_cache_dir();
+    drop(s);
+
+    let mut history = DownloadHistory::open(&cache_dir)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    history.reset_usage().await.map_err(|e| e.to_string())
+}
+
 /// Remove a specific history record
 #[tauri::command]
 pub async fn download_history_remove(
@@ -1319,20 +1642,42 @@ pub async fn disk_space_check(path: String, required: u64) -> Result<bool, Strin
         .map_err(|e| e.to_string())
 }
 
-/// Extract an archive to a destination directory
+/// Extract an archive to a destination directory, emitting
+/// `download-extract-progress` events while it runs
 ///
 /// Supports: .zip, .tar.gz, .tgz, .tar.xz, .txz, .tar.bz2, .tbz2, .tar.zst, .tzst, .7z
 #[tauri::command]
 pub async fn download_extract(
     archive_path: String,
     dest_path: String,
+    app: AppHandle,
 ) -> Result<Vec<String>, String> {
     let archive = std::path::Path::new(&archive_path);
     let dest = std::path::Path::new(&dest_path);
 
-    let files = crate::core::installer::extract_archive(archive, dest)
-        .await
-        .map_err(|e| e.to_string())?;
+    #[derive(Serialize)]
+    struct ExtractProgressPayload {
+        archive_path: String,
+        progress: crate::core::archive::ExtractProgress,
+    }
+    let event_archive_path = archive_path.clone();
+    let on_progress = crate::core::archive::throttle_progress(
+        std::time::Duration::from_millis(200),
+        move |progress| {
+            let _ = app.emit(
+                "download-extract-progress",
+                &ExtractProgressPayload {
+                    archive_path: event_archive_path.clone(),
+                    progress,
+                },
+            );
+        },
+    );
+
+    let files =
+        crate::core::archive::extract_archive_with_progress(archive, dest, Some(on_progress))
+            .await
+            .map_err(|e| e.to_string())?;
 
     Ok(files.into_iter().map(|p| p.display().to_string()).collect())
 }
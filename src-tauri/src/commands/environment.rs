@@ -1,8 +1,26 @@
 use crate::cache::MetadataCache;
+use crate::core::custom_env_type::CUSTOM_PROVIDER_PREFIX;
+use crate::core::env_disk_usage::{
+    self, EnvDiskUsageReport, EnvProjectPin, EnvReclaimSuggestions, EnvUsageLog,
+};
+use crate::core::env_resolution::{self, EnvResolutionReport, PathMatch, ProfileManagerInit};
+use crate::core::eol_advice::{self, EnvUpgradeAdvice};
+use crate::core::install_validation::InstallValidation;
+use crate::core::node_globals::{self, NodePackageManager};
+use crate::core::operation_queue::{JobKind, SharedOperationQueue};
+use crate::core::path_shadow;
+use crate::core::pin_file::{self, PinFileFormat};
+use crate::core::smoke_test::{self, SmokeTestLog, SmokeTestReport};
+use crate::core::version_import::{
+    self, AdoptedInstall, AdoptedInstalls, ForeignManager, VersionImportInventory,
+};
+use crate::core::version_sync::{AvailableVersions, VersionIndex, VersionSyncResult};
 use crate::core::{
-    DetectedEnvironment, EnvCleanupResult, EnvUpdateCheckResult, EnvironmentInfo,
-    EnvironmentManager, SharedVersionCache,
+    DetectedEnvironment, EnvBatchItemState, EnvCleanupResult, EnvInstallBatch,
+    EnvInstallBatchRequest, EnvInstallBatchResult, EnvUpdateCheckResult, EnvironmentInfo,
+    EnvironmentManager, InstallPrerequisite, SharedPrerequisites, SharedVersionCache,
 };
+use crate::download::ProgressCoalescer;
 use crate::provider::{
     CppCompilerMetadata, EnvironmentProvider, InstallProgressEvent, InstallRequest, InstallStage,
     InstalledVersion, ProgressSender, Provider, ProviderRegistry, SystemEnvironmentProvider,
@@ -20,6 +38,9 @@
 const ENV_SYSTEM_DETECT_TTL: i64 = 300; // 5 minutes
 const ENV_INSTALLED_TTL: i64 = 60; // 1 minute
 const ENV_PROVIDERS_TTL: i64 = 600; // 10 minutes (rarely changes)
+const ENV_DISK_USAGE_TTL: i64 = 600; // 10 minutes (directory walks are expensive)
+const ENV_EOL_TTL: i64 = 86400; // 24 hours, matches the in-memory EOL cache
+const VERSION_INDEX_TTL: i64 = 30 * 86400; // 30 days; freshness is judged by last_synced
 
 async fn open_env_metadata_cache(
     config: &crate::commands::config::SharedSettings,
@@ -33,6 +54,19 @@ async fn open_env_metadata_cache(
         .map_err(|e| e.to_string())
 }
 
+/// Read the environment list written by `env_list`, if it is still fresh.
+pub(crate) async fn cached_env_list(
+    config: &crate::commands::config::SharedSettings,
+) -> Option<Vec<EnvironmentInfo>> {
+    let mut cache = open_env_metadata_cache(config, ENV_LIST_CACHE_TTL)
+        .await
+        .ok()?;
+    match cache.get::<Vec<EnvironmentInfo>>("env:list").await {
+        Ok(Some(cached)) if !cached.is_stale => Some(cached.data),
+        _ => None,
+    }
+}
+
 /// Invalidate environment-related cache keys (called after install/uninstall/switch).
 pub async fn invalidate_env_caches(config: &crate::commands::config::SharedSettings) {
     if let Ok(mut cache) = open_env_metadata_cache(config, ENV_LIST_CACHE_TTL).await {
@@ -40,6 +74,7 @@ pub async fn invalidate_env_caches(config: &crate::commands::config::SharedSetti
         let _ = cache.remove("env:list").await;
         let _ = cache.remove("env:system_all").await;
         let _ = cache.remove("env:providers").await;
+        let _ = cache.remove("env:disk_usage").await;
         // Prefix removals (env:system:node, env:versions:node:fnm, env:available:node:fnm, etc.)
         let _ = cache.remove_by_prefix("env:system:").await;
         let _ = cache.remove_by_prefix("env:versions:").await;
@@ -47,6 +82,38 @@ pub async fn invalidate_env_caches(config: &crate::commands::config::SharedSetti
     }
 }
 
+/// Drop download-cache references held by an uninstalled version so its
+/// artifacts become eligible for eviction again.
+async fn release_cache_references(
+    config: &crate::commands::config::SharedSettings,
+    env_type: &str,
+    version: &str,
+) {
+    let cache_dir = config.read().await.get_cache_dir();
+    let mut dl_cache = match crate::cache::DownloadCache::open(&cache_dir).await {
+        Ok(cache) => cache,
+        Err(e) => {
+            log::warn!("Failed to open download cache to release references: {}", e);
+            return;
+        }
+    };
+    let logical_env_type = EnvironmentManager::logical_env_type(env_type);
+    let mut env_types = vec![logical_env_type.as_str()];
+    if env_type != logical_env_type {
+        env_types.push(env_type);
+    }
+    for env_type in env_types {
+        if let Err(e) = dl_cache.release_references(env_type, version).await {
+            log::warn!(
+                "Failed to release cache references for {}@{}: {}",
+                env_type,
+                version,
+                e
+            );
+        }
+    }
+}
+
 /// Cancellation tokens for ongoing installations
 pub type CancellationTokens = Arc<RwLock<HashMap<String, Arc<std::sync::atomic::AtomicBool>>>>;
 
@@ -76,7 +143,7 @@ fn sanitize_detection_sources(
     }
 }
 
-async fn enabled_detection_sources_for_env_type(
+pub(crate) async fn enabled_detection_sources_for_env_type(
     env_type: &str,
     config: &crate::commands::config::SharedSettings,
 ) -> Vec<String> {
@@ -376,6 +443,9 @@ pub struct EnvInstallProgress {
     pub total_size: Option<u64>,
     pub speed: Option<f64>,
     pub error: Option<String>,
+    /// Post-install smoke tests, attached to the final completed event
+    #[serde(default)]
+    pub smoke_test: Option<SmokeTestReport>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -418,6 +488,104 @@ fn build_install_progress(
         total_size,
         speed,
         error,
+        smoke_test: None,
+    }
+}
+
+/// Run the post-install smoke tests of `env_type@version` with the activation
+/// environment launches use, and remember the outcome. `None` when the tests
+/// could not be set up at all.
+async fn run_install_smoke_tests(
+    manager: &EnvironmentManager,
+    env_type: &str,
+    version: &str,
+    provider_id: Option<&str>,
+    config: &crate::commands::config::SharedSettings,
+) -> Option<SmokeTestReport> {
+    let custom = config.read().await.general.smoke_tests.clone();
+    let env = manager
+        .get_env_modifications(env_type, version, provider_id)
+        .await
+        .unwrap_or_else(|e| {
+            log::warn!(
+                "No activation environment for {}@{} smoke tests: {}",
+                env_type,
+                version,
+                e
+            );
+            Default::default()
+        });
+
+    match smoke_test::run_smoke_tests(env_type, version, &env, &custom).await {
+        Ok(report) => {
+            SmokeTestLog::record(&report).await;
+            Some(report)
+        }
+        Err(e) => {
+            log::warn!(
+                "Could not run smoke tests for {}@{}: {}",
+                env_type,
+                version,
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Receives every progress payload of an install, e.g. to aggregate a batch
+pub(crate) type InstallProgressObserver = Arc<dyn Fn(&EnvInstallProgress) + Send + Sync>;
+
+/// Sends install progress to the frontend and to an optional observer
+#[derive(Clone)]
+struct InstallProgressSink {
+    app: AppHandle,
+    observer: Option<InstallProgressObserver>,
+    coalescer: Arc<std::sync::Mutex<ProgressCoalescer<EnvInstallProgress>>>,
+}
+
+impl InstallProgressSink {
+    fn new(
+        app: AppHandle,
+        observer: Option<InstallProgressObserver>,
+        interval: std::time::Duration,
+    ) -> Self {
+        Self {
+            app,
+            observer,
+            coalescer: Arc::new(std::sync::Mutex::new(ProgressCoalescer::new(interval))),
+        }
+    }
+
+    /// Download updates are coalesced to one per interval; any other phase
+    /// or a terminal state goes out at once, after the last pending update.
+    fn emit(&self, progress: EnvInstallProgress) {
+        let key = format!("{}@{}", progress.env_type, progress.version);
+        let coalesce =
+            progress.terminal_state.is_none() && progress.phase == Some(EnvInstallPhase::Download);
+        let ready: Vec<EnvInstallProgress> = match self.coalescer.lock() {
+            Ok(mut coalescer) if coalesce => {
+                coalescer.push(&key, progress);
+                coalescer
+                    .poll(std::time::Instant::now())
+                    .into_iter()
+                    .flatten()
+                    .map(|(_, p)| p)
+                    .collect()
+            }
+            Ok(mut coalescer) => coalescer.take(&key).into_iter().chain([progress]).collect(),
+            Err(_) => vec![progress],
+        };
+        for progress in ready {
+            self.send(progress);
+        }
+    }
+
+    fn send(&self, progress: EnvInstallProgress) {
+        if let Some(observer) = &self.observer {
+            observer(&progress);
+        }
+        let _ = self.app.emit("env-install-progress", progress);
     }
 }
 
@@ -542,6 +710,41 @@ mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    #[test]
+    fn package_manager_override_is_validated() {
+        assert_eq!(parse_package_manager(None).unwrap(), None);
+        assert_eq!(parse_package_manager(Some(" ")).unwrap(), None);
+        assert_eq!(
+            parse_package_manager(Some("pnpm")).unwrap(),
+            Some(NodePackageManager::Pnpm)
+        );
+        assert!(parse_package_manager(Some("deno")).is_err());
+    }
+
+    #[test]
+    fn global_package_info_serializes_manager_only_for_node() {
+        let python = GlobalPackageInfo {
+            name: "black".into(),
+            version: "24.3.0".into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&python).unwrap(),
+            serde_json::json!({"name": "black", "version": "24.3.0", "bins": []})
+        );
+
+        let node = GlobalPackageInfo {
+            name: "typescript".into(),
+            version: "5.4.5".into(),
+            bins: vec!["tsc".into()],
+            package_manager: Some(NodePackageManager::Bun),
+        };
+        assert_eq!(
+            serde_json::to_value(&node).unwrap()["packageManager"],
+            "bun"
+        );
+    }
+
     #[test]
     fn lifecycle_enforces_canonical_order_and_single_terminal() {
         let mut lifecycle = InstallLifecycle::default();
@@ -750,18 +953,62 @@ fn resolve_provider_executable_path_matches_detected_version() {
         let resolved = resolve_provider_executable_path(&installed_versions, "3.12.1");
         assert_eq!(resolved, Some(version_path.to_string_lossy().to_string()));
     }
+
+    fn pin(source: &str, path: &str, version: &str) -> DetectedEnvironment {
+        DetectedEnvironment {
+            env_type: "node".into(),
+            version: version.into(),
+            source: source.into(),
+            source_path: Some(PathBuf::from(path)),
+            source_type: "local".into(),
+            resolved_via: None,
+            resolved_version: None,
+            suggested_version: None,
+        }
+    }
+
+    #[test]
+    fn shadowing_pins_ahead_of_written_file_are_reported() {
+        let candidates = vec![
+            pin(".nvmrc", "/p/.nvmrc", "18.17.0"),
+            pin(".node-version", "/p/.node-version", "20"),
+            pin(".tool-versions", "/p/.tool-versions", "20.11.0"),
+            pin("mise.toml", "/mise.toml", "16"),
+        ];
+        let written = PathBuf::from("/p/.tool-versions");
+        let warnings = shadowing_pin_warnings(&candidates, Some(&written), "20.11.0");
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("/p/.nvmrc"));
+        assert!(warnings[0].contains("18.17.0"));
+
+        // Without a known file, the first matching pin is the new one.
+        assert!(shadowing_pin_warnings(&candidates[1..], None, "20.11.0").is_empty());
+        assert_eq!(
+            shadowing_pin_warnings(&candidates, None, "20.11.0").len(),
+            1
+        );
+    }
+
+    #[test]
+    fn written_pin_missing_from_detection_is_reported() {
+        let candidates = vec![pin(".nvmrc", "/p/.nvmrc", "18")];
+        let written = PathBuf::from("/p/mise.toml");
+        let warnings = shadowing_pin_warnings(&candidates, Some(&written), "20");
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("not an enabled detection source"));
+    }
 }
 
-#[tauri::command]
-pub async fn env_list(
-    force: Option<bool>,
-    registry: State<'_, SharedRegistry>,
-    config: State<'_, crate::commands::config::SharedSettings>,
+/// Installed environments, served from the metadata cache unless `force`
+pub(crate) async fn load_env_list(
+    force: bool,
+    registry: &SharedRegistry,
+    config: &crate::commands::config::SharedSettings,
 ) -> Result<Vec<EnvironmentInfo>, String> {
     let cache_key = "env:list";
 
-    if !force.unwrap_or(false) {
-        if let Ok(mut cache) = open_env_metadata_cache(config.inner(), ENV_LIST_CACHE_TTL).await {
+    if !force {
+        if let Ok(mut cache) = open_env_metadata_cache(config, ENV_LIST_CACHE_TTL).await {
             if let Ok(Some(cached)) = cache.get::<Vec<EnvironmentInfo>>(cache_key).await {
                 if !cached.is_stale {
                     return Ok(cached.data);
@@ -770,14 +1017,14 @@ pub async fn env_list(
         }
     }
 
-    let manager = EnvironmentManager::new(registry.inner().clone());
+    let manager = EnvironmentManager::new(registry.clone());
     let max_concurrency = config.read().await.startup.max_concurrent_scans;
     let result = manager
         .list_environments_with_concurrency(max_concurrency)
         .await
         .map_err(|e| e.to_string())?;
 
-    if let Ok(mut cache) = open_env_metadata_cache(config.inner(), ENV_LIST_CACHE_TTL).await {
+    if let Ok(mut cache) = open_env_metadata_cache(config, ENV_LIST_CACHE_TTL).await {
         let _ = cache
             .set_with_ttl(cache_key, &result, ENV_LIST_CACHE_TTL)
             .await;
@@ -786,6 +1033,15 @@ pub async fn env_list(
     Ok(result)
 }
 
+#[tauri::command]
+pub async fn env_list(
+    force: Option<bool>,
+    registry: State<'_, SharedRegistry>,
+    config: State<'_, crate::commands::config::SharedSettings>,
+) -> Result<Vec<EnvironmentInfo>, String> {
+    load_env_list(force.unwrap_or(false), registry.inner(), config.inner()).await
+}
+
 #[tauri::command]
 pub async fn env_get(
     env_type: String,
@@ -800,7 +1056,52 @@ pub async fn env_get(
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn env_install(
+    env_type: String,
+    version: String,
+    provider_id: Option<String>,
+    registry: State<'_, SharedRegistry>,
+    tokens: State<'_, CancellationTokens>,
+    config: State<'_, crate::commands::config::SharedSettings>,
+    jobs: State<'_, SharedOperationQueue>,
+    app: AppHandle,
+) -> Result<(), String> {
+    // The job shares the install's cancellation token so jobs_cancel and
+    // env_install_cancel stop the same operation
+    let cancel_token = Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let job = jobs
+        .inner()
+        .start(
+            JobKind::EnvInstall,
+            format!("Install {} {}", env_type, version),
+            serde_json::json!({
+                "envType": env_type,
+                "version": version,
+                "providerId": provider_id,
+            }),
+            Some(cancel_token.clone()),
+        )
+        .await;
+
+    let result = run_env_install(
+        env_type,
+        version,
+        provider_id,
+        registry,
+        tokens,
+        config,
+        app,
+        cancel_token,
+        None,
+    )
+    .await;
+    job.finish(&result).await;
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn run_env_install(
     env_type: String,
     version: String,
     provider_id: Option<String>,
@@ -808,58 +1109,65 @@ pub async fn env_install(
     tokens: State<'_, CancellationTokens>,
     config: State<'_, crate::commands::config::SharedSettings>,
     app: AppHandle,
+    cancel_token: Arc<std::sync::atomic::AtomicBool>,
+    observer: Option<InstallProgressObserver>,
 ) -> Result<(), String> {
     let manager = EnvironmentManager::new(registry.inner().clone());
     let mut lifecycle = InstallLifecycle::default();
+    let progress_interval =
+        std::time::Duration::from_millis(config.read().await.general.progress_event_interval_ms);
+    let progress_sink = InstallProgressSink::new(app, observer, progress_interval);
 
-    // Create a cancellation token for this installation
+    // Register the cancellation token for this installation. A key held by a
+    // different token means the same version is already being installed.
     let cancel_key = get_cancel_key(&env_type, &version);
-    let cancel_token = Arc::new(std::sync::atomic::AtomicBool::new(false));
     {
         let mut tokens_guard = tokens.write().await;
+        if let Some(existing) = tokens_guard.get(&cancel_key) {
+            if !Arc::ptr_eq(existing, &cancel_token) {
+                return Err(format!(
+                    "{} {} is already being installed",
+                    env_type, version
+                ));
+            }
+        }
         tokens_guard.insert(cancel_key.clone(), cancel_token.clone());
     }
 
     if let Err(reason) = lifecycle.transition(EnvInstallPhase::Resolve) {
         let current_phase = lifecycle.current_phase;
         let _ = lifecycle.mark_terminal(EnvInstallTerminalState::Failed);
-        let _ = app.emit(
-            "env-install-progress",
-            build_lifecycle_failure_progress(
-                &env_type,
-                &version,
-                current_phase,
-                reason.clone(),
-                None,
-            ),
-        );
+        progress_sink.emit(build_lifecycle_failure_progress(
+            &env_type,
+            &version,
+            current_phase,
+            reason.clone(),
+            None,
+        ));
         let mut tokens_guard = tokens.write().await;
         tokens_guard.remove(&cancel_key);
         return Err(reason);
     }
 
-    let _ = app.emit(
-        "env-install-progress",
-        build_install_progress(
-            &env_type,
-            &version,
-            Some(EnvInstallPhase::Resolve),
-            None,
-            None,
-            None,
-            Some("Resolving provider and install plan".to_string()),
-            None,
-            None,
-            None,
-            None,
-            None,
-            2.0,
-            None,
-            None,
-            None,
-            None,
-        ),
-    );
+    progress_sink.emit(build_install_progress(
+        &env_type,
+        &version,
+        Some(EnvInstallPhase::Resolve),
+        None,
+        None,
+        None,
+        Some("Resolving provider and install plan".to_string()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        2.0,
+        None,
+        None,
+        None,
+        None,
+    ));
 
     // Resolve provider and install with progress
     let (logical_env_type, provider_key, provider) = match manager
@@ -873,28 +1181,25 @@ pub async fn env_install(
             let (retryable, retry_after_seconds, attempt, max_attempts) =
                 retry_guidance(failure_class);
             let _ = lifecycle.mark_terminal(EnvInstallTerminalState::Failed);
-            let _ = app.emit(
-                "env-install-progress",
-                build_install_progress(
-                    &env_type,
-                    &version,
-                    Some(EnvInstallPhase::Resolve),
-                    Some(EnvInstallTerminalState::Failed),
-                    Some(failure_class),
-                    None,
-                    Some("Failed while resolving provider".to_string()),
-                    None,
-                    retryable,
-                    retry_after_seconds,
-                    attempt,
-                    max_attempts,
-                    0.0,
-                    None,
-                    None,
-                    None,
-                    Some(error_message.clone()),
-                ),
-            );
+            progress_sink.emit(build_install_progress(
+                &env_type,
+                &version,
+                Some(EnvInstallPhase::Resolve),
+                Some(EnvInstallTerminalState::Failed),
+                Some(failure_class),
+                None,
+                Some("Failed while resolving provider".to_string()),
+                None,
+                retryable,
+                retry_after_seconds,
+                attempt,
+                max_attempts,
+                0.0,
+                None,
+                None,
+                None,
+                Some(error_message.clone()),
+            ));
 
             let mut tokens_guard = tokens.write().await;
             tokens_guard.remove(&cancel_key);
@@ -912,16 +1217,13 @@ pub async fn env_install(
     if let Err(reason) = lifecycle.transition(EnvInstallPhase::SelectArtifact) {
         let current_phase = lifecycle.current_phase;
         let _ = lifecycle.mark_terminal(EnvInstallTerminalState::Failed);
-        let _ = app.emit(
-            "env-install-progress",
-            build_lifecycle_failure_progress(
-                &env_type,
-                &version,
-                current_phase,
-                reason.clone(),
-                Some(artifact.clone()),
-            ),
-        );
+        progress_sink.emit(build_lifecycle_failure_progress(
+            &env_type,
+            &version,
+            current_phase,
+            reason.clone(),
+            Some(artifact.clone()),
+        ));
         let mut tokens_guard = tokens.write().await;
         tokens_guard.remove(&cancel_key);
         return Err(reason);
@@ -933,28 +1235,25 @@ pub async fn env_install(
         logical_env_type,
         crate::platform::env::current_platform(),
     ));
-    let _ = app.emit(
-        "env-install-progress",
-        build_install_progress(
-            &env_type,
-            &version,
-            Some(EnvInstallPhase::SelectArtifact),
-            None,
-            None,
-            Some(artifact.clone()),
-            Some("Deterministically selecting installation artifact".to_string()),
-            selection_rationale,
-            None,
-            None,
-            None,
-            None,
-            8.0,
-            None,
-            None,
-            None,
-            None,
-        ),
-    );
+    progress_sink.emit(build_install_progress(
+        &env_type,
+        &version,
+        Some(EnvInstallPhase::SelectArtifact),
+        None,
+        None,
+        Some(artifact.clone()),
+        Some("Deterministically selecting installation artifact".to_string()),
+        selection_rationale,
+        None,
+        None,
+        None,
+        None,
+        8.0,
+        None,
+        None,
+        None,
+        None,
+    ));
 
     // Create a progress channel
     let (tx, mut rx): (ProgressSender, mpsc::Receiver<InstallProgressEvent>) = mpsc::channel(32);
@@ -962,50 +1261,44 @@ pub async fn env_install(
     if let Err(reason) = lifecycle.transition(EnvInstallPhase::Download) {
         let current_phase = lifecycle.current_phase;
         let _ = lifecycle.mark_terminal(EnvInstallTerminalState::Failed);
-        let _ = app.emit(
-            "env-install-progress",
-            build_lifecycle_failure_progress(
-                &env_type,
-                &version,
-                current_phase,
-                reason.clone(),
-                Some(artifact.clone()),
-            ),
-        );
+        progress_sink.emit(build_lifecycle_failure_progress(
+            &env_type,
+            &version,
+            current_phase,
+            reason.clone(),
+            Some(artifact.clone()),
+        ));
         let mut tokens_guard = tokens.write().await;
         tokens_guard.remove(&cancel_key);
         return Err(reason);
     }
 
-    let _ = app.emit(
-        "env-install-progress",
-        build_install_progress(
-            &env_type,
-            &version,
-            Some(EnvInstallPhase::Download),
-            None,
-            None,
-            Some(artifact.clone()),
-            Some("Starting transfer".to_string()),
-            None,
-            None,
-            None,
-            None,
-            None,
-            10.0,
-            None,
-            None,
-            None,
-            None,
-        ),
-    );
-
-    // Clone values for the progress forwarding task
-    let env_type_clone = env_type.clone();
-    let version_clone = version.clone();
-    let app_clone = app.clone();
-    let cancel_token_clone = cancel_token.clone();
-    let artifact_clone = artifact.clone();
+    progress_sink.emit(build_install_progress(
+        &env_type,
+        &version,
+        Some(EnvInstallPhase::Download),
+        None,
+        None,
+        Some(artifact.clone()),
+        Some("Starting transfer".to_string()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        10.0,
+        None,
+        None,
+        None,
+        None,
+    ));
+
+    // Clone values for the progress forwarding task
+    let env_type_clone = env_type.clone();
+    let version_clone = version.clone();
+    let progress_sink_clone = progress_sink.clone();
+    let cancel_token_clone = cancel_token.clone();
+    let artifact_clone = artifact.clone();
 
     // Spawn a task to forward progress events to the frontend
     let progress_task = tokio::spawn(async move {
@@ -1031,42 +1324,39 @@ pub async fn env_install(
                 Some(event.message.clone())
             };
 
-            let _ = app_clone.emit(
-                "env-install-progress",
-                build_install_progress(
-                    &env_type_clone,
-                    &version_clone,
-                    Some(EnvInstallPhase::Download),
-                    None,
-                    None,
-                    Some(artifact_clone.clone()),
-                    stage_message,
-                    None,
-                    None,
-                    None,
-                    None,
-                    None,
-                    progress,
-                    if event.downloaded_bytes > 0 {
-                        Some(event.downloaded_bytes)
-                    } else {
-                        None
-                    },
-                    event.total_bytes,
-                    if event.speed_bps > 0.0 {
-                        Some(event.speed_bps)
-                    } else {
-                        None
-                    },
-                    None,
-                ),
-            );
+            progress_sink_clone.emit(build_install_progress(
+                &env_type_clone,
+                &version_clone,
+                Some(EnvInstallPhase::Download),
+                None,
+                None,
+                Some(artifact_clone.clone()),
+                stage_message,
+                None,
+                None,
+                None,
+                None,
+                None,
+                progress,
+                if event.downloaded_bytes > 0 {
+                    Some(event.downloaded_bytes)
+                } else {
+                    None
+                },
+                event.total_bytes,
+                if event.speed_bps > 0.0 {
+                    Some(event.speed_bps)
+                } else {
+                    None
+                },
+                None,
+            ));
         }
     });
 
     // Create the install request
     let request = InstallRequest {
-        name: logical_env_type,
+        name: logical_env_type.clone(),
         version: Some(version.clone()),
         global: true,
         force: false,
@@ -1076,30 +1366,24 @@ pub async fn env_install(
     if cancel_token.load(std::sync::atomic::Ordering::SeqCst) {
         if let Err(reason) = lifecycle.mark_terminal(EnvInstallTerminalState::Cancelled) {
             let current_phase = lifecycle.current_phase;
-            let _ = app.emit(
-                "env-install-progress",
-                build_lifecycle_failure_progress(
-                    &env_type,
-                    &version,
-                    current_phase,
-                    reason.clone(),
-                    Some(artifact.clone()),
-                ),
-            );
+            progress_sink.emit(build_lifecycle_failure_progress(
+                &env_type,
+                &version,
+                current_phase,
+                reason.clone(),
+                Some(artifact.clone()),
+            ));
             let mut tokens_guard = tokens.write().await;
             tokens_guard.remove(&cancel_key);
             return Err(reason);
         }
 
-        let _ = app.emit(
-            "env-install-progress",
-            build_cancelled_progress(
-                &env_type,
-                &version,
-                lifecycle.current_phase.or(Some(EnvInstallPhase::Download)),
-                Some(artifact.clone()),
-            ),
-        );
+        progress_sink.emit(build_cancelled_progress(
+            &env_type,
+            &version,
+            lifecycle.current_phase.or(Some(EnvInstallPhase::Download)),
+            Some(artifact.clone()),
+        ));
 
         // Cleanup cancellation token
         let mut tokens_guard = tokens.write().await;
@@ -1123,34 +1407,29 @@ pub async fn env_install(
     if cancel_token.load(std::sync::atomic::Ordering::SeqCst) {
         if let Err(reason) = lifecycle.mark_terminal(EnvInstallTerminalState::Cancelled) {
             let current_phase = lifecycle.current_phase;
-            let _ = app.emit(
-                "env-install-progress",
-                build_lifecycle_failure_progress(
-                    &env_type,
-                    &version,
-                    current_phase,
-                    reason.clone(),
-                    Some(artifact.clone()),
-                ),
-            );
-            return Err(reason);
-        }
-
-        let _ = app.emit(
-            "env-install-progress",
-            build_cancelled_progress(
+            progress_sink.emit(build_lifecycle_failure_progress(
                 &env_type,
                 &version,
-                lifecycle.current_phase.or(Some(EnvInstallPhase::Download)),
+                current_phase,
+                reason.clone(),
                 Some(artifact.clone()),
-            ),
-        );
+            ));
+            return Err(reason);
+        }
+
+        progress_sink.emit(build_cancelled_progress(
+            &env_type,
+            &version,
+            lifecycle.current_phase.or(Some(EnvInstallPhase::Download)),
+            Some(artifact.clone()),
+        ));
         return Err("Installation cancelled by user".to_string());
     }
 
     // Handle result
     match result {
         Ok(_receipt) => {
+            let mut smoke_report = None;
             for (phase, progress, stage_message) in [
                 (
                     EnvInstallPhase::Verify,
@@ -1171,84 +1450,92 @@ pub async fn env_install(
                 if let Err(reason) = lifecycle.transition(phase) {
                     let current_phase = lifecycle.current_phase;
                     let _ = lifecycle.mark_terminal(EnvInstallTerminalState::Failed);
-                    let _ = app.emit(
-                        "env-install-progress",
-                        build_lifecycle_failure_progress(
-                            &env_type,
-                            &version,
-                            current_phase,
-                            reason.clone(),
-                            Some(artifact.clone()),
-                        ),
-                    );
-                    return Err(reason);
-                }
-
-                let _ = app.emit(
-                    "env-install-progress",
-                    build_install_progress(
-                        &env_type,
-                        &version,
-                        Some(phase),
-                        None,
-                        None,
-                        Some(artifact.clone()),
-                        Some(stage_message),
-                        None,
-                        None,
-                        None,
-                        None,
-                        None,
-                        progress,
-                        None,
-                        None,
-                        None,
-                        None,
-                    ),
-                );
-            }
-
-            if let Err(reason) = lifecycle.mark_terminal(EnvInstallTerminalState::Completed) {
-                let current_phase = lifecycle.current_phase;
-                let _ = app.emit(
-                    "env-install-progress",
-                    build_lifecycle_failure_progress(
+                    progress_sink.emit(build_lifecycle_failure_progress(
                         &env_type,
                         &version,
                         current_phase,
                         reason.clone(),
                         Some(artifact.clone()),
-                    ),
-                );
-                return Err(reason);
-            }
-
-            // Invalidate environment caches after successful install
-            invalidate_env_caches(config.inner()).await;
+                    ));
+                    return Err(reason);
+                }
 
-            // Emit final success event
-            let _ = app.emit(
-                "env-install-progress",
-                build_install_progress(
+                progress_sink.emit(build_install_progress(
                     &env_type,
                     &version,
-                    Some(EnvInstallPhase::Finalize),
-                    Some(EnvInstallTerminalState::Completed),
+                    Some(phase),
+                    None,
                     None,
-                    Some(artifact),
-                    Some("Installation completed".to_string()),
+                    Some(artifact.clone()),
+                    Some(stage_message),
                     None,
                     None,
                     None,
                     None,
                     None,
-                    100.0,
+                    progress,
                     None,
                     None,
                     None,
                     None,
-                ),
+                ));
+
+                // A failing smoke test keeps the install but marks it unverified
+                if phase == EnvInstallPhase::Verify {
+                    smoke_report = run_install_smoke_tests(
+                        &manager,
+                        &logical_env_type,
+                        &version,
+                        Some(&provider_key),
+                        config.inner(),
+                    )
+                    .await;
+                }
+            }
+
+            if let Err(reason) = lifecycle.mark_terminal(EnvInstallTerminalState::Completed) {
+                let current_phase = lifecycle.current_phase;
+                progress_sink.emit(build_lifecycle_failure_progress(
+                    &env_type,
+                    &version,
+                    current_phase,
+                    reason.clone(),
+                    Some(artifact.clone()),
+                ));
+                return Err(reason);
+            }
+
+            // Invalidate environment caches after successful install
+            invalidate_env_caches(config.inner()).await;
+
+            // Emit final success event
+            let stage_message = match &smoke_report {
+                Some(report) if !report.passed => {
+                    "Installation completed, smoke tests failed".to_string()
+                }
+                _ => "Installation completed".to_string(),
+            };
+            let mut completed = build_install_progress(
+                &env_type,
+                &version,
+                Some(EnvInstallPhase::Finalize),
+                Some(EnvInstallTerminalState::Completed),
+                None,
+                Some(artifact),
+                Some(stage_message),
+                None,
+                None,
+                None,
+                None,
+                None,
+                100.0,
+                None,
+                None,
+                None,
+                None,
             );
+            completed.smoke_test = smoke_report;
+            progress_sink.emit(completed);
             Ok(())
         }
         Err(e) => {
@@ -1261,56 +1548,223 @@ pub async fn env_install(
                 retry_guidance(failure_class);
             if let Err(reason) = lifecycle.mark_terminal(EnvInstallTerminalState::Failed) {
                 let current_phase = lifecycle.current_phase;
-                let _ = app.emit(
-                    "env-install-progress",
-                    build_lifecycle_failure_progress(
-                        &env_type,
-                        &version,
-                        current_phase,
-                        reason.clone(),
-                        Some(artifact.clone()),
-                    ),
-                );
+                progress_sink.emit(build_lifecycle_failure_progress(
+                    &env_type,
+                    &version,
+                    current_phase,
+                    reason.clone(),
+                    Some(artifact.clone()),
+                ));
                 return Err(reason);
             }
 
             // Emit final error event
-            let _ = app.emit(
-                "env-install-progress",
-                build_install_progress(
-                    &env_type,
-                    &version,
-                    lifecycle.current_phase.or(Some(EnvInstallPhase::Download)),
-                    Some(EnvInstallTerminalState::Failed),
-                    Some(failure_class),
-                    Some(artifact),
-                    Some("Installation failed".to_string()),
-                    None,
-                    retryable,
-                    retry_after_seconds,
-                    attempt,
-                    max_attempts,
-                    0.0,
-                    None,
-                    None,
-                    None,
-                    Some(error_message.clone()),
-                ),
-            );
+            progress_sink.emit(build_install_progress(
+                &env_type,
+                &version,
+                lifecycle.current_phase.or(Some(EnvInstallPhase::Download)),
+                Some(EnvInstallTerminalState::Failed),
+                Some(failure_class),
+                Some(artifact),
+                Some("Installation failed".to_string()),
+                None,
+                retryable,
+                retry_after_seconds,
+                attempt,
+                max_attempts,
+                0.0,
+                None,
+                None,
+                None,
+                Some(error_message.clone()),
+            ));
             Err(error_message)
         }
     }
 }
 
+/// Install several environments at once, at most `general.parallel_env_installs`
+/// at a time. Each install keeps its `env_install_cancel` key; cancelling the
+/// job cancels them all. Shims are regenerated once after every install ends.
+#[tauri::command]
+pub async fn env_install_batch(
+    requests: Vec<EnvInstallBatchRequest>,
+    registry: State<'_, SharedRegistry>,
+    tokens: State<'_, CancellationTokens>,
+    config: State<'_, crate::commands::config::SharedSettings>,
+    jobs: State<'_, SharedOperationQueue>,
+    app: AppHandle,
+) -> Result<EnvInstallBatchResult, String> {
+    let batch = EnvInstallBatch::new(requests);
+    if batch.requests().is_empty() {
+        return Err("No environments to install".to_string());
+    }
+    let (max_concurrent, root_dir) = {
+        let s = config.read().await;
+        (s.general.parallel_env_installs as usize, s.get_root_dir())
+    };
+
+    let job = jobs
+        .inner()
+        .start(
+            JobKind::BatchInstall,
+            format!("Install {} environment(s)", batch.requests().len()),
+            serde_json::json!({
+                "batchId": batch.id(),
+                "requests": batch.requests(),
+            }),
+            Some(batch.cancel_token()),
+        )
+        .await;
+
+    // Register every token up front so queued installs can be cancelled too
+    {
+        let mut tokens_guard = tokens.write().await;
+        for request in batch.requests() {
+            let key = request.key();
+            if let Some(token) = batch.item_token(&key) {
+                tokens_guard.entry(key).or_insert(token);
+            }
+        }
+    }
+
+    let manager = EnvironmentManager::new(registry.inner().clone());
+    let prerequisites = SharedPrerequisites::default();
+    let items = batch
+        .run(
+            max_concurrent,
+            |request, token, reporter| {
+                let (manager, prerequisites) = (&manager, &prerequisites);
+                let (registry, tokens, config) = (registry.clone(), tokens.clone(), config.clone());
+                let app = app.clone();
+                async move {
+                    if let Ok((_, provider_key, _)) = manager
+                        .resolve_provider(
+                            &request.env_type,
+                            request.provider_id.as_deref(),
+                            Some(&request.version),
+                        )
+                        .await
+                    {
+                        for prerequisite in InstallPrerequisite::for_provider(&provider_key) {
+                            prerequisites.ensure(*prerequisite).await?;
+                        }
+                    }
+
+                    let observer: InstallProgressObserver =
+                        Arc::new(move |progress: &EnvInstallProgress| {
+                            reporter.report(progress.progress, progress.stage_message.clone())
+                        });
+                    run_env_install(
+                        request.env_type,
+                        request.version,
+                        request.provider_id,
+                        registry,
+                        tokens,
+                        config,
+                        app,
+                        token,
+                        Some(observer),
+                    )
+                    .await
+                }
+            },
+            |progress| {
+                job.progress(
+                    Some(progress.overall_progress as f64),
+                    Some(progress.key.clone()),
+                );
+                let _ = app.emit("env-install-batch-progress", progress);
+            },
+        )
+        .await;
+
+    // Installs that never started still hold their tokens
+    {
+        let mut tokens_guard = tokens.write().await;
+        for request in batch.requests() {
+            let key = request.key();
+            let Some(ours) = batch.item_token(&key) else {
+                continue;
+            };
+            if tokens_guard
+                .get(&key)
+                .is_some_and(|token| Arc::ptr_eq(token, &ours))
+            {
+                tokens_guard.remove(&key);
+            }
+        }
+    }
+
+    let mut result = EnvInstallBatchResult::new(batch.id().to_string(), items);
+    if result.count(EnvBatchItemState::Completed) > 0 {
+        let regenerated = match crate::core::ShimManager::new(&root_dir).await {
+            Ok(mut shims) => shims.regenerate_all().await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = regenerated {
+            log::warn!("Failed to regenerate shims after batch install: {}", e);
+            result.shim_error = Some(e.to_string());
+        }
+    }
+
+    let failed = result.count(EnvBatchItemState::Failed);
+    let outcome = if batch
+        .cancel_token()
+        .load(std::sync::atomic::Ordering::SeqCst)
+    {
+        Err("Installation cancelled by user".to_string())
+    } else if failed == 0 {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} of {} environment(s) failed",
+            failed,
+            result.items.len()
+        ))
+    };
+    job.finish(&outcome).await;
+    Ok(result)
+}
+
 #[tauri::command]
 pub async fn env_uninstall(
     env_type: String,
     version: String,
     provider_id: Option<String>,
+    remove_foreign_files: Option<bool>,
     registry: State<'_, SharedRegistry>,
     config: State<'_, crate::commands::config::SharedSettings>,
 ) -> Result<(), String> {
     let manager = EnvironmentManager::new(registry.inner().clone());
+    let logical_env_type = EnvironmentManager::logical_env_type(&env_type);
+    if let Some(adopted) = foreign_adopted_install(
+        &manager,
+        &logical_env_type,
+        &version,
+        provider_id.as_deref(),
+    )
+    .await
+    {
+        // Adopted installs belong to another manager: unregister them and
+        // only delete their files when explicitly asked to.
+        let root_dir = config.read().await.get_root_dir();
+        version_import::deactivate_adopted(&root_dir, &adopted)
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut registry = AdoptedInstalls::load_default().await;
+        registry.remove(adopted.manager, &adopted.env_type, &adopted.version);
+        registry.save_default().await.map_err(|e| e.to_string())?;
+        if remove_foreign_files.unwrap_or(false) {
+            tokio::fs::remove_dir_all(&adopted.install_path)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        invalidate_env_caches(config.inner()).await;
+        release_cache_references(config.inner(), &env_type, &version).await;
+        return Ok(());
+    }
+
     manager
         .uninstall_version(&env_type, &version, provider_id.as_deref())
         .await
@@ -1318,6 +1772,7 @@ pub async fn env_uninstall(
 
     // Invalidate environment caches after successful uninstall
     invalidate_env_caches(config.inner()).await;
+    release_cache_references(config.inner(), &env_type, &version).await;
 
     Ok(())
 }
@@ -1333,6 +1788,12 @@ pub struct EnvVersionMutationResult {
     pub success: bool,
     pub status: String,
     pub message: Option<String>,
+    /// Pin file written by `set_local`, when it is not the provider's own
+    #[serde(default)]
+    pub pin_file: Option<String>,
+    /// Other pins that take precedence over the one just written
+    #[serde(default)]
+    pub warnings: Vec<String>,
 }
 
 fn normalize_version_token(raw: &str) -> String {
@@ -1360,6 +1821,65 @@ fn versions_compatible(expected: &str, actual: &str) -> bool {
     expected == actual || actual.starts_with(&expected) || expected.starts_with(&actual)
 }
 
+/// Provider with its own install of `version`. A managed install takes
+/// precedence over an adopted copy of the same version.
+async fn managed_install_provider(
+    manager: &EnvironmentManager,
+    env_type: &str,
+    version: &str,
+    provider_id: Option<&str>,
+) -> Option<String> {
+    let (_, resolved_id, provider) = manager
+        .resolve_provider(env_type, provider_id, Some(version))
+        .await
+        .ok()?;
+    let wanted = normalize_version_token(version);
+    provider
+        .list_installed_versions()
+        .await
+        .ok()?
+        .iter()
+        .any(|v| normalize_version_token(&v.version) == wanted)
+        .then_some(resolved_id)
+}
+
+/// The adopted install for `version`, unless the resolved provider manages
+/// that manager's install tree itself (e.g. adopting from nvm while the nvm
+/// provider is active) or has its own install of the version, in which
+/// case the provider handles the version.
+async fn foreign_adopted_install(
+    manager: &EnvironmentManager,
+    env_type: &str,
+    version: &str,
+    provider_id: Option<&str>,
+) -> Option<AdoptedInstall> {
+    let adopted = AdoptedInstalls::load_default()
+        .await
+        .find(env_type, version)
+        .cloned()?;
+    if let Ok((_, resolved_id, _)) = manager
+        .resolve_provider(env_type, provider_id, Some(version))
+        .await
+    {
+        if adopted.manager.owns_provider(&resolved_id) {
+            return None;
+        }
+    }
+    if let Some(managed_by) =
+        managed_install_provider(manager, env_type, version, provider_id).await
+    {
+        log::info!(
+            "Using the {} install of {} {} over the one adopted from {}",
+            managed_by,
+            env_type,
+            version,
+            adopted.manager.as_str()
+        );
+        return None;
+    }
+    Some(adopted)
+}
+
 #[tauri::command]
 pub async fn env_use_global(
     env_type: String,
@@ -1370,6 +1890,39 @@ pub async fn env_use_global(
 ) -> Result<EnvVersionMutationResult, String> {
     let manager = EnvironmentManager::new(registry.inner().clone());
     let logical_env_type = EnvironmentManager::logical_env_type(&env_type);
+    if let Some(adopted) = foreign_adopted_install(
+        &manager,
+        &logical_env_type,
+        &version,
+        provider_id.as_deref(),
+    )
+    .await
+    {
+        let root_dir = config.read().await.get_root_dir();
+        let result = version_import::activate_adopted(&root_dir, &adopted).await;
+        invalidate_env_caches(config.inner()).await;
+        let (success, message) = match result {
+            Ok(_) => (true, None),
+            Err(e) => (false, Some(e.to_string())),
+        };
+        return Ok(EnvVersionMutationResult {
+            env_type: logical_env_type,
+            operation: "set_global".to_string(),
+            requested_version: version.clone(),
+            effective_version: success.then_some(version),
+            source_type: "adopted".to_string(),
+            success,
+            status: if success {
+                "verified".to_string()
+            } else {
+                "failed".to_string()
+            },
+            message,
+            pin_file: None,
+            warnings: Vec::new(),
+        });
+    }
+
     let (_logical, _provider_key, provider) = manager
         .resolve_provider(&logical_env_type, provider_id.as_deref(), Some(&version))
         .await
@@ -1413,36 +1966,110 @@ pub async fn env_use_global(
             "verification_failed".to_string()
         },
         message,
+        pin_file: None,
+        warnings: Vec::new(),
     })
 }
 
+/// Warnings for pins that shadow the one written for `version`. `written` is
+/// the pin file when known; otherwise the first candidate matching `version`
+/// is taken as the new pin.
+fn shadowing_pin_warnings(
+    candidates: &[DetectedEnvironment],
+    written: Option<&std::path::Path>,
+    version: &str,
+) -> Vec<String> {
+    let position = match written {
+        Some(path) => candidates
+            .iter()
+            .position(|c| c.source_path.as_deref() == Some(path)),
+        None => candidates
+            .iter()
+            .position(|c| versions_compatible(version, &c.version)),
+    };
+    let Some(position) = position else {
+        return match written {
+            Some(path) => vec![format!(
+                "{} is not an enabled detection source, so the new pin is ignored",
+                path.display()
+            )],
+            None => Vec::new(),
+        };
+    };
+
+    candidates[..position]
+        .iter()
+        .filter(|c| !versions_compatible(version, &c.version))
+        .map(|c| {
+            format!(
+                "{} pins `{}` and takes precedence over the new `{}` pin",
+                c.source_path
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|| c.source.clone()),
+                c.version,
+                version
+            )
+        })
+        .collect()
+}
+
 #[tauri::command]
 pub async fn env_use_local(
     env_type: String,
     version: String,
     project_path: String,
     provider_id: Option<String>,
+    pin_file_format: Option<PinFileFormat>,
     registry: State<'_, SharedRegistry>,
     config: State<'_, crate::commands::config::SharedSettings>,
 ) -> Result<EnvVersionMutationResult, String> {
     let manager = EnvironmentManager::new(registry.inner().clone());
     let logical_env_type = EnvironmentManager::logical_env_type(&env_type);
-    let (_logical, _provider_key, provider) = manager
-        .resolve_provider(&logical_env_type, provider_id.as_deref(), Some(&version))
-        .await
-        .map_err(|e| e.to_string())?;
-    provider
-        .set_local_version(std::path::Path::new(&project_path), &version)
-        .await
-        .map_err(|e| e.to_string())?;
+    let project_dir = std::path::Path::new(&project_path);
+    // A format passed for a project is remembered for it and overrides the
+    // global choice from then on
+    let format = match pin_file_format {
+        Some(format) => format,
+        None => config.read().await.get_pin_file_format(project_dir),
+    };
+
+    let pin_file = match format {
+        PinFileFormat::Native => {
+            let (_logical, _provider_key, provider) = manager
+                .resolve_provider(&logical_env_type, provider_id.as_deref(), Some(&version))
+                .await
+                .map_err(|e| e.to_string())?;
+            provider
+                .set_local_version(project_dir, &version)
+                .await
+                .map_err(|e| e.to_string())?;
+            None
+        }
+        format => Some(
+            pin_file::write_pin(project_dir, &logical_env_type, &version, format)
+                .await
+                .map_err(|e| e.to_string())?,
+        ),
+    };
+    if let Some(format) = pin_file_format {
+        let mut settings = config.write().await;
+        if settings.set_project_pin_file_format(project_dir, format) {
+            settings.save().await.map_err(|e| e.to_string())?;
+        }
+    }
 
     let sources = enabled_detection_sources_for_env_type(&logical_env_type, config.inner()).await;
+    let candidates = crate::core::project_env_detect::detect_env_version_candidates(
+        &logical_env_type,
+        project_dir,
+        &sources,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    let warnings = shadowing_pin_warnings(&candidates, pin_file.as_deref(), &version);
     let detected = manager
-        .detect_version_with_sources(
-            &logical_env_type,
-            std::path::Path::new(&project_path),
-            &sources,
-        )
+        .detect_version_with_sources(&logical_env_type, project_dir, &sources)
         .await
         .map_err(|e| e.to_string())?;
     let effective_version = detected.as_ref().map(|d| d.version.clone());
@@ -1481,23 +2108,254 @@ pub async fn env_use_local(
             "verification_failed".to_string()
         },
         message,
+        pin_file: pin_file.map(|p| p.display().to_string()),
+        warnings,
     })
 }
 
+/// How `import_from_version_managers` treats the selected installs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionImportMode {
+    /// Only report what was found
+    #[default]
+    Preview,
+    /// Register the installs so they can be used in place
+    Adopt,
+    /// Reinstall matching versions through Cognia's providers
+    Migrate,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionImportSelection {
+    pub manager: ForeignManager,
+    pub env_type: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionImportOutcome {
+    pub manager: ForeignManager,
+    pub env_type: String,
+    pub version: String,
+    /// `adopted`, `migrated`, `skipped` or `failed`
+    pub action: String,
+    pub success: bool,
+    pub provider_id: Option<String>,
+    pub originals_removed: bool,
+    pub error: Option<String>,
+}
+
+impl VersionImportOutcome {
+    fn new(install: &version_import::ForeignInstall, action: &str) -> Self {
+        Self {
+            manager: install.manager,
+            env_type: install.env_type.clone(),
+            version: install.version.clone(),
+            action: action.to_string(),
+            success: action != "failed",
+            provider_id: None,
+            originals_removed: false,
+            error: None,
+        }
+    }
+
+    fn failed(install: &version_import::ForeignInstall, error: String) -> Self {
+        Self {
+            error: Some(error),
+            ..Self::new(install, "failed")
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionImportReport {
+    pub mode: VersionImportMode,
+    pub inventory: VersionImportInventory,
+    pub outcomes: Vec<VersionImportOutcome>,
+    pub succeeded: usize,
+    pub failed: usize,
+}
+
+async fn migrate_foreign_install(
+    manager: &EnvironmentManager,
+    install: &version_import::ForeignInstall,
+    remove_originals: bool,
+    root_dir: &std::path::Path,
+    adopted: &mut AdoptedInstalls,
+) -> VersionImportOutcome {
+    let (provider_id, provider) = match manager
+        .resolve_provider(&install.env_type, None, Some(&install.version))
+        .await
+    {
+        Ok((_, provider_id, provider)) => (provider_id, provider),
+        Err(e) => return VersionImportOutcome::failed(install, e.to_string()),
+    };
+    let mut outcome = VersionImportOutcome {
+        provider_id: Some(provider_id.clone()),
+        ..VersionImportOutcome::new(install, "migrated")
+    };
+    if install.manager.owns_provider(&provider_id) {
+        // The provider already manages this tree; reinstalling would target
+        // the very files we might be asked to remove.
+        outcome.action = "skipped".to_string();
+        outcome.error = Some(format!(
+            "Already managed by the {} provider",
+            provider.display_name()
+        ));
+        return outcome;
+    }
+
+    let already_installed = provider
+        .list_installed_versions()
+        .await
+        .map(|versions| versions.iter().any(|v| v.version == install.version))
+        .unwrap_or(false);
+    if !already_installed {
+        if let Err(e) = manager
+            .install_version(&install.env_type, &install.version, Some(&provider_id))
+            .await
+        {
+            return VersionImportOutcome {
+                provider_id: Some(provider_id),
+                ..VersionImportOutcome::failed(install, e.to_string())
+            };
+        }
+    }
+
+    if let Some(previous) = adopted.remove(install.manager, &install.env_type, &install.version) {
+        if let Err(e) = version_import::deactivate_adopted(root_dir, &previous).await {
+            log::warn!("Failed to remove shims for adopted install: {}", e);
+        }
+    }
+    if remove_originals {
+        match tokio::fs::remove_dir_all(&install.install_path).await {
+            Ok(()) => outcome.originals_removed = true,
+            Err(e) => {
+                outcome.error = Some(format!(
+                    "Installed, but removing {} failed: {}",
+                    install.install_path.display(),
+                    e
+                ))
+            }
+        }
+    }
+    outcome
+}
+
+/// Discover versions installed by nvm, pyenv, sdkman and asdf. `preview`
+/// only returns the inventory; `adopt` registers the selected installs for
+/// in-place use; `migrate` installs them through Cognia's providers and,
+/// with `removeOriginals`, deletes the originals afterwards. Each version
+/// succeeds or fails on its own. `selections` defaults to everything found.
 #[tauri::command]
-pub async fn env_detect(
-    env_type: String,
-    start_path: String,
+pub async fn import_from_version_managers(
+    mode: Option<VersionImportMode>,
+    selections: Option<Vec<VersionImportSelection>>,
+    remove_originals: Option<bool>,
     registry: State<'_, SharedRegistry>,
     config: State<'_, crate::commands::config::SharedSettings>,
-) -> Result<Option<DetectedEnvironment>, String> {
+) -> Result<VersionImportReport, String> {
+    let mode = mode.unwrap_or_default();
+    let mut adopted = AdoptedInstalls::load_default().await;
+    let snapshot = adopted.clone();
+    let mut inventory =
+        tokio::task::spawn_blocking(move || version_import::scan_default_inventory(&snapshot))
+            .await
+            .map_err(|e| e.to_string())?;
+
+    let selected: Vec<_> = inventory
+        .installs()
+        .filter(|install| match &selections {
+            Some(selections) => selections.iter().any(|s| {
+                s.manager == install.manager
+                    && s.env_type == install.env_type
+                    && s.version == install.version
+            }),
+            None => true,
+        })
+        .cloned()
+        .collect();
+
+    let mut outcomes = Vec::new();
     let manager = EnvironmentManager::new(registry.inner().clone());
-    let logical_env_type = EnvironmentManager::logical_env_type(&env_type);
-    let sources = enabled_detection_sources_for_env_type(&logical_env_type, config.inner()).await;
+    match mode {
+        VersionImportMode::Preview => {}
+        VersionImportMode::Adopt => {
+            for install in &selected {
+                if let Some(provider_id) =
+                    managed_install_provider(&manager, &install.env_type, &install.version, None)
+                        .await
+                {
+                    outcomes.push(VersionImportOutcome {
+                        error: Some(format!("Already installed by {}", provider_id)),
+                        provider_id: Some(provider_id),
+                        ..VersionImportOutcome::new(install, "skipped")
+                    });
+                    continue;
+                }
+                outcomes.push(match adopted.add(AdoptedInstall::from_foreign(install)) {
+                    Ok(()) => VersionImportOutcome::new(install, "adopted"),
+                    Err(e) => VersionImportOutcome::failed(install, e.to_string()),
+                });
+            }
+        }
+        VersionImportMode::Migrate => {
+            let root_dir = config.read().await.get_root_dir();
+            let remove_originals = remove_originals.unwrap_or(false);
+            for install in &selected {
+                outcomes.push(
+                    migrate_foreign_install(
+                        &manager,
+                        install,
+                        remove_originals,
+                        &root_dir,
+                        &mut adopted,
+                    )
+                    .await,
+                );
+            }
+        }
+    }
+
+    if mode != VersionImportMode::Preview {
+        adopted.save_default().await.map_err(|e| e.to_string())?;
+        invalidate_env_caches(config.inner()).await;
+        for env in &mut inventory.env_types {
+            for install in &mut env.installs {
+                install.adopted = adopted.is_adopted(install);
+            }
+        }
+    }
+
+    let failed = outcomes.iter().filter(|o| !o.success).count();
+    Ok(VersionImportReport {
+        mode,
+        inventory,
+        succeeded: outcomes.len() - failed,
+        failed,
+        outcomes,
+    })
+}
+
+/// Version of `env_type` that applies at `start_path`, using the enabled
+/// detection sources
+pub(crate) async fn detect_env(
+    env_type: &str,
+    start_path: &str,
+    registry: &SharedRegistry,
+    config: &crate::commands::config::SharedSettings,
+) -> Result<Option<DetectedEnvironment>, String> {
+    let manager = EnvironmentManager::new(registry.clone());
+    let logical_env_type = EnvironmentManager::logical_env_type(env_type);
+    let sources = enabled_detection_sources_for_env_type(&logical_env_type, config).await;
     manager
         .detect_version_with_sources(
             &logical_env_type,
-            std::path::Path::new(&start_path),
+            std::path::Path::new(start_path),
             &sources,
         )
         .await
@@ -1505,10 +2363,20 @@ pub async fn env_detect(
 }
 
 #[tauri::command]
-pub async fn env_detect_all(
+pub async fn env_detect(
+    env_type: String,
     start_path: String,
     registry: State<'_, SharedRegistry>,
     config: State<'_, crate::commands::config::SharedSettings>,
+) -> Result<Option<DetectedEnvironment>, String> {
+    detect_env(&env_type, &start_path, registry.inner(), config.inner()).await
+}
+
+/// Every environment type pinned or detected at `start_path`
+pub(crate) async fn detect_all_envs(
+    start_path: &str,
+    registry: &SharedRegistry,
+    config: &crate::commands::config::SharedSettings,
 ) -> Result<Vec<DetectedEnvironment>, String> {
     let all_types = crate::provider::SystemEnvironmentType::all();
 
@@ -1516,16 +2384,15 @@ pub async fn env_detect_all(
     let mut source_map: Vec<(String, Vec<String>)> = Vec::with_capacity(all_types.len());
     for env in &all_types {
         let env_type = env.env_type();
-        let sources = enabled_detection_sources_for_env_type(env_type, config.inner()).await;
+        let sources = enabled_detection_sources_for_env_type(env_type, config).await;
         source_map.push((env_type.to_string(), sources));
     }
 
     // Run detections in parallel
-    let registry_inner = registry.inner().clone();
     let mut futures = Vec::with_capacity(source_map.len());
     for (env_type, sources) in source_map {
-        let reg = registry_inner.clone();
-        let path = start_path.clone();
+        let reg = registry.clone();
+        let path = start_path.to_string();
         futures.push(async move {
             let manager = EnvironmentManager::new(reg);
             manager
@@ -1543,6 +2410,72 @@ pub async fn env_detect_all(
     Ok(detected)
 }
 
+#[tauri::command]
+pub async fn env_detect_all(
+    start_path: String,
+    registry: State<'_, SharedRegistry>,
+    config: State<'_, crate::commands::config::SharedSettings>,
+) -> Result<Vec<DetectedEnvironment>, String> {
+    detect_all_envs(&start_path, registry.inner(), config.inner()).await
+}
+
+fn version_index_key(env_type: &str, provider_id: Option<&str>) -> String {
+    format!(
+        "env:version-index:{}:{}",
+        env_type,
+        provider_id.unwrap_or("auto")
+    )
+}
+
+/// Sync the persisted version index for `env_type`/`provider_id` with
+/// upstream and store the result.
+async fn sync_version_index(
+    env_type: &str,
+    provider_id: Option<&str>,
+    force_full: bool,
+    registry: &SharedRegistry,
+    config: &crate::commands::config::SharedSettings,
+) -> Result<VersionSyncResult, String> {
+    let index_key = version_index_key(env_type, provider_id);
+    let mut md_cache = open_env_metadata_cache(config, VERSION_INDEX_TTL)
+        .await
+        .ok();
+    let previous = match md_cache.as_mut() {
+        Some(cache) => cache
+            .get::<VersionIndex>(&index_key)
+            .await
+            .ok()
+            .flatten()
+            .map(|cached| cached.data),
+        None => None,
+    };
+
+    let manager = EnvironmentManager::new(registry.clone());
+    let (logical, index, stats) = manager
+        .sync_available_versions(env_type, provider_id, previous, force_full)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if let Some(cache) = md_cache.as_mut() {
+        let _ = cache
+            .set_with_ttl(&index_key, &index, VERSION_INDEX_TTL)
+            .await;
+    }
+
+    Ok(VersionSyncResult {
+        env_type: logical,
+        provider_id: index.provider_id,
+        versions: index.versions,
+        last_synced: index.last_synced,
+        stats,
+    })
+}
+
+/// Available versions of `env_type`, with when they were last synced.
+///
+/// Served from memory, then from the persisted version index while it is
+/// fresh; otherwise upstream is asked only for what changed since the last
+/// sync. `force` skips the freshness check but still syncs incrementally.
 #[tauri::command]
 pub async fn env_available_versions(
     env_type: String,
@@ -1551,13 +2484,8 @@ pub async fn env_available_versions(
     registry: State<'_, SharedRegistry>,
     version_cache: State<'_, SharedVersionCache>,
     config: State<'_, crate::commands::config::SharedSettings>,
-) -> Result<Vec<crate::provider::VersionInfo>, String> {
+) -> Result<AvailableVersions, String> {
     let cache_key = format!("{}:{}", env_type, provider_id.as_deref().unwrap_or(""));
-    let metadata_key = format!(
-        "env:available:{}:{}",
-        env_type,
-        provider_id.as_deref().unwrap_or("auto")
-    );
 
     // Layer 1: in-memory VersionCache (fastest, survives within session)
     if !force.unwrap_or(false) {
@@ -1565,38 +2493,70 @@ pub async fn env_available_versions(
             return Ok(cached);
         }
 
-        // Layer 2: MetadataCache (SQLite, survives across restarts)
-        if let Ok(mut md_cache) = open_env_metadata_cache(config.inner(), ENV_PROVIDERS_TTL).await {
-            if let Ok(Some(cached)) = md_cache
-                .get::<Vec<crate::provider::VersionInfo>>(&metadata_key)
-                .await
-            {
-                if !cached.is_stale {
-                    // Warm in-memory cache from disk cache
-                    version_cache
-                        .set(cache_key.clone(), cached.data.clone())
-                        .await;
-                    return Ok(cached.data);
+        // Layer 2: persisted version index, if synced recently
+        let index_key = version_index_key(&env_type, provider_id.as_deref());
+        if let Ok(mut md_cache) = open_env_metadata_cache(config.inner(), VERSION_INDEX_TTL).await {
+            if let Ok(Some(cached)) = md_cache.get::<VersionIndex>(&index_key).await {
+                let age = chrono::Utc::now() - cached.data.last_synced;
+                if age < chrono::Duration::seconds(ENV_PROVIDERS_TTL) {
+                    let available = AvailableVersions::from(&cached.data);
+                    version_cache.set(cache_key, available.clone()).await;
+                    return Ok(available);
                 }
             }
         }
     }
 
-    let manager = EnvironmentManager::new(registry.inner().clone());
-    let versions = manager
-        .get_available_versions(&env_type, provider_id.as_deref())
-        .await
-        .map_err(|e| e.to_string())?;
+    let result = sync_version_index(
+        &env_type,
+        provider_id.as_deref(),
+        false,
+        registry.inner(),
+        config.inner(),
+    )
+    .await?;
+    let available = AvailableVersions {
+        versions: result.versions,
+        last_synced: Some(result.last_synced),
+    };
+    version_cache.set(cache_key, available.clone()).await;
 
-    // Write to both cache layers
-    version_cache.set(cache_key, versions.clone()).await;
-    if let Ok(mut md_cache) = open_env_metadata_cache(config.inner(), ENV_PROVIDERS_TTL).await {
-        let _ = md_cache
-            .set_with_ttl(&metadata_key, &versions, ENV_PROVIDERS_TTL)
-            .await;
-    }
+    Ok(available)
+}
+
+/// Sync available versions of `env_type` with upstream now. Without `force`
+/// only changes since the last sync are fetched; with it the full list is
+/// refetched so versions removed upstream are marked yanked.
+#[tauri::command]
+pub async fn env_refresh_versions(
+    env_type: String,
+    provider_id: Option<String>,
+    force: bool,
+    registry: State<'_, SharedRegistry>,
+    version_cache: State<'_, SharedVersionCache>,
+    config: State<'_, crate::commands::config::SharedSettings>,
+) -> Result<VersionSyncResult, String> {
+    let result = sync_version_index(
+        &env_type,
+        provider_id.as_deref(),
+        force,
+        registry.inner(),
+        config.inner(),
+    )
+    .await?;
+
+    let cache_key = format!("{}:{}", env_type, provider_id.as_deref().unwrap_or(""));
+    version_cache
+        .set(
+            cache_key,
+            AvailableVersions {
+                versions: result.versions.clone(),
+                last_synced: Some(result.last_synced),
+            },
+        )
+        .await;
 
-    Ok(versions)
+    Ok(result)
 }
 
 #[tauri::command]
@@ -1661,6 +2621,8 @@ pub async fn env_list_providers(
                 "deno" => ("deno", "Deno runtime version management"),
                 "zig" => ("zig", "Zig version management via ziglang.org downloads"),
                 "fvm" => ("dart", "Flutter Version Manager for Dart/Flutter SDK"),
+                "juliaup" => ("julia", "juliaup - the official Julia version multiplexer"),
+                "rig" => ("r", "rig - the R installation manager"),
                 "mise" => (
                     "polyglot",
                     "Modern polyglot version manager (successor to rtx/asdf)",
@@ -1722,7 +2684,7 @@ pub async fn env_resolve_alias(
     let alias_lower = alias.to_lowercase();
     let alias_env_type = match env_type.as_str() {
         "fnm" | "nvm" | "volta" => "node",
-        "pyenv" | "uv" | "conda" | "pipx" => "python",
+        "pyenv" | "uv" | "conda" | "pipx" | "pixi" => "python",
         "goenv" => "go",
         "rustup" => "rust",
         "rbenv" => "ruby",
@@ -1737,6 +2699,8 @@ pub async fn env_resolve_alias(
         "dotnet" => "dotnet",
         "deno" => "deno",
         "zig" => "zig",
+        "juliaup" => "julia",
+        "rig" => "r",
         _ => env_type.as_str(),
     };
 
@@ -1898,16 +2862,24 @@ pub async fn env_detect_system_all(
     force: Option<bool>,
     config: State<'_, crate::commands::config::SharedSettings>,
 ) -> Result<Vec<SystemEnvironmentInfo>, String> {
+    Ok(detect_system_environments(force.unwrap_or(false), config.inner()).await)
+}
+
+/// System environment scan behind `env_detect_system_all`, cached for
+/// `ENV_SYSTEM_DETECT_TTL` unless `force` is set
+pub(crate) async fn detect_system_environments(
+    force: bool,
+    config: &crate::commands::config::SharedSettings,
+) -> Vec<SystemEnvironmentInfo> {
     use crate::provider::{SystemEnvironmentProvider, SystemEnvironmentType};
 
     let cache_key = "env:system_all";
 
-    if !force.unwrap_or(false) {
-        if let Ok(mut cache) = open_env_metadata_cache(config.inner(), ENV_SYSTEM_DETECT_TTL).await
-        {
+    if !force {
+        if let Ok(mut cache) = open_env_metadata_cache(config, ENV_SYSTEM_DETECT_TTL).await {
             if let Ok(Some(cached)) = cache.get::<Vec<SystemEnvironmentInfo>>(cache_key).await {
                 if !cached.is_stale {
-                    return Ok(cached.data);
+                    return cached.data;
                 }
             }
         }
@@ -1940,13 +2912,13 @@ pub async fn env_detect_system_all(
         }
     }
 
-    if let Ok(mut cache) = open_env_metadata_cache(config.inner(), ENV_SYSTEM_DETECT_TTL).await {
+    if let Ok(mut cache) = open_env_metadata_cache(config, ENV_SYSTEM_DETECT_TTL).await {
         let _ = cache
             .set_with_ttl(cache_key, &results, ENV_SYSTEM_DETECT_TTL)
             .await;
     }
 
-    Ok(results)
+    results
 }
 
 /// Detect current versions from all available environment providers while
@@ -2066,6 +3038,7 @@ pub async fn env_detect_system(
         "fortran" | "gfortran" => Some(SystemEnvironmentType::Fortran),
         "c" | "gcc" | "cc" => Some(SystemEnvironmentType::C),
         "cpp" | "c++" | "g++" | "clang++" | "clang" => Some(SystemEnvironmentType::Cpp),
+        "cuda" | "nvcc" => Some(SystemEnvironmentType::Cuda),
         _ => None,
     };
 
@@ -2122,7 +3095,9 @@ pub async fn env_detect_system(
 
 /// Get the environment type mapping from provider ID to logical environment type
 #[tauri::command]
-pub async fn env_get_type_mapping() -> Result<std::collections::HashMap<String, String>, String> {
+pub async fn env_get_type_mapping(
+    registry: State<'_, SharedRegistry>,
+) -> Result<std::collections::HashMap<String, String>, String> {
     let mut mapping = std::collections::HashMap::new();
 
     // Version managers to environment types
@@ -2145,8 +3120,11 @@ pub async fn env_get_type_mapping() -> Result<std::collections::HashMap<String,
     mapping.insert("asdf".to_string(), "polyglot".to_string());
     mapping.insert("nix".to_string(), "polyglot".to_string());
     mapping.insert("pipx".to_string(), "python".to_string());
+    mapping.insert("pixi".to_string(), "python".to_string());
     mapping.insert("zig".to_string(), "zig".to_string());
     mapping.insert("fvm".to_string(), "dart".to_string());
+    mapping.insert("juliaup".to_string(), "julia".to_string());
+    mapping.insert("rig".to_string(), "r".to_string());
     mapping.insert("sdkman-groovy".to_string(), "groovy".to_string());
     mapping.insert("sdkman-gradle".to_string(), "gradle".to_string());
     mapping.insert("sdkman-maven".to_string(), "maven".to_string());
@@ -2192,19 +3170,26 @@ pub async fn env_get_type_mapping() -> Result<std::collections::HashMap<String,
     mapping.insert("system-c".to_string(), "c".to_string());
     mapping.insert("system-cpp".to_string(), "cpp".to_string());
 
+    // User-defined environment types
+    for env_type in registry.read().await.custom_environment_types() {
+        mapping.insert(format!("{}{}", CUSTOM_PROVIDER_PREFIX, env_type), env_type);
+    }
+
     Ok(mapping)
 }
 
-/// Verify that a specific version was installed successfully
+/// Verify that a specific version was installed successfully. Installed
+/// versions are smoke tested again, which also refreshes their unverified mark.
 #[tauri::command]
 pub async fn env_verify_install(
     env_type: String,
     version: String,
     provider_id: Option<String>,
     registry: State<'_, SharedRegistry>,
+    config: State<'_, crate::commands::config::SharedSettings>,
 ) -> Result<EnvVerifyResult, String> {
     let manager = EnvironmentManager::new(registry.inner().clone());
-    let (_logical_env_type, _provider_key, provider) = manager
+    let (logical_env_type, provider_key, provider) = manager
         .resolve_provider(&env_type, provider_id.as_deref(), Some(&version))
         .await
         .map_err(|e| e.to_string())?;
@@ -2221,14 +3206,42 @@ pub async fn env_verify_install(
     // Get the current version to verify switching worked
     let current = provider.get_current_version().await.ok().flatten();
 
+    let smoke_test = if found {
+        run_install_smoke_tests(
+            &manager,
+            &logical_env_type,
+            &version,
+            Some(&provider_key),
+            config.inner(),
+        )
+        .await
+    } else {
+        None
+    };
+
     Ok(EnvVerifyResult {
         installed: found,
         provider_available,
         current_version: current,
         requested_version: version,
+        smoke_test,
     })
 }
 
+/// Approve the current command line of the smoke test at `index` in
+/// `general.smoke_tests`, so it runs although it is not allowlisted
+#[tauri::command]
+pub async fn env_smoke_test_approve(
+    index: usize,
+    config: State<'_, crate::commands::config::SharedSettings>,
+) -> Result<(), String> {
+    let mut settings = config.write().await;
+    settings
+        .approve_smoke_test(index)
+        .map_err(|e| e.to_string())?;
+    settings.save().await.map_err(|e| e.to_string())
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EnvVerifyResult {
@@ -2236,6 +3249,16 @@ pub struct EnvVerifyResult {
     pub provider_available: bool,
     pub current_version: Option<String>,
     pub requested_version: String,
+    pub smoke_test: Option<SmokeTestReport>,
+}
+
+/// Installed version together with the outcome of its latest smoke tests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvInstalledVersion {
+    #[serde(flatten)]
+    pub installed: InstalledVersion,
+    /// The latest post-install smoke tests of this version failed
+    pub unverified: bool,
 }
 
 /// Get installed versions for a specific environment provider
@@ -2246,12 +3269,13 @@ pub async fn env_installed_versions(
     force: Option<bool>,
     registry: State<'_, SharedRegistry>,
     config: State<'_, crate::commands::config::SharedSettings>,
-) -> Result<Vec<crate::provider::InstalledVersion>, String> {
+) -> Result<Vec<EnvInstalledVersion>, String> {
     let cache_key = format!(
         "env:versions:{}:{}",
         &env_type,
         provider_id.as_deref().unwrap_or("auto")
     );
+    let logical_env_type = crate::core::environment::normalize_env_type(&env_type);
 
     if !force.unwrap_or(false) {
         if let Ok(mut cache) = open_env_metadata_cache(config.inner(), ENV_INSTALLED_TTL).await {
@@ -2260,7 +3284,7 @@ pub async fn env_installed_versions(
                 .await
             {
                 if !cached.is_stale {
-                    return Ok(cached.data);
+                    return Ok(mark_unverified(&logical_env_type, cached.data).await);
                 }
             }
         }
@@ -2283,7 +3307,22 @@ pub async fn env_installed_versions(
             .await;
     }
 
-    Ok(versions)
+    Ok(mark_unverified(&logical_env_type, versions).await)
+}
+
+/// Flag versions whose latest smoke tests failed
+async fn mark_unverified(
+    env_type: &str,
+    versions: Vec<InstalledVersion>,
+) -> Vec<EnvInstalledVersion> {
+    let log = SmokeTestLog::load().await.unwrap_or_default();
+    versions
+        .into_iter()
+        .map(|installed| EnvInstalledVersion {
+            unverified: log.is_unverified(env_type, &installed.version),
+            installed,
+        })
+        .collect()
 }
 
 /// Get the current active version for a specific environment provider
@@ -2371,6 +3410,278 @@ pub async fn env_get_version_eol(
         .map_err(|e| e.to_string())
 }
 
+/// EOL cycles for an environment type. Served from `EolCache` (which falls back
+/// to expired data when the API is unreachable), then from the on-disk metadata
+/// cache so advice still works offline after a restart.
+async fn load_eol_cycles(
+    env_type: &str,
+    eol_cache: &SharedEolCache,
+    config: &crate::commands::config::SharedSettings,
+) -> (
+    Option<Vec<crate::core::eol::EolCycleInfo>>,
+    bool,
+    Option<String>,
+) {
+    let cache_key = format!("env:eol:{}", env_type);
+    match eol_cache.get_eol_data_with_staleness(env_type).await {
+        Ok((cycles, stale)) => {
+            if !stale {
+                if let Ok(mut cache) = open_env_metadata_cache(config, ENV_EOL_TTL).await {
+                    let _ = cache.set_with_ttl(&cache_key, &cycles, ENV_EOL_TTL).await;
+                }
+            }
+            (Some(cycles), stale, None)
+        }
+        Err(e) => {
+            let persisted = match open_env_metadata_cache(config, ENV_EOL_TTL).await {
+                Ok(mut cache) => cache
+                    .get::<Vec<crate::core::eol::EolCycleInfo>>(&cache_key)
+                    .await
+                    .ok()
+                    .flatten(),
+                Err(_) => None,
+            };
+            match persisted {
+                Some(cached) => (Some(cached.data), cached.is_stale, Some(e.to_string())),
+                None => (None, false, Some(e.to_string())),
+            }
+        }
+    }
+}
+
+/// Per-version EOL status and upgrade recommendations for an environment,
+/// with the known projects still pinning each version.
+/// Known projects are `project_dirs` plus working directories seen in launches.
+#[tauri::command]
+pub async fn env_upgrade_advice(
+    env_type: String,
+    warning_days: Option<i64>,
+    project_dirs: Option<Vec<String>>,
+    registry: State<'_, SharedRegistry>,
+    config: State<'_, crate::commands::config::SharedSettings>,
+    eol_cache: State<'_, SharedEolCache>,
+) -> Result<EnvUpgradeAdvice, String> {
+    let manager = EnvironmentManager::new(registry.inner().clone());
+    let env = manager
+        .get_environment(&env_type, None)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let available: Vec<String> = manager
+        .get_available_versions(&env.env_type, Some(&env.provider_id))
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|v| !v.deprecated && !v.yanked)
+        .map(|v| v.version)
+        .collect();
+    let installed: Vec<(String, bool)> = env
+        .installed_versions
+        .iter()
+        .map(|v| {
+            let is_current =
+                v.is_current || env.current_version.as_deref() == Some(v.version.as_str());
+            (v.version.clone(), is_current)
+        })
+        .collect();
+
+    let (cycles, eol_stale, eol_error) =
+        load_eol_cycles(&env.env_type, eol_cache.inner(), config.inner()).await;
+
+    let usage = EnvUsageLog::load().await.unwrap_or_default();
+    let scanned_projects =
+        env_disk_usage::collect_project_dirs(&project_dirs.unwrap_or_default(), &usage);
+    let pins = detect_project_pins(&manager, &scanned_projects, &[env.env_type.as_str()]).await;
+
+    let mut advice = eol_advice::build_upgrade_advice(&eol_advice::UpgradeAdviceInput {
+        env_type: &env.env_type,
+        provider_id: &env.provider_id,
+        installed: &installed,
+        available: &available,
+        cycles: cycles.as_deref(),
+        pins: &pins,
+        warning_days: warning_days
+            .filter(|days| *days >= 0)
+            .unwrap_or(eol_advice::DEFAULT_EOL_WARNING_DAYS),
+        today: chrono::Utc::now().date_naive(),
+    });
+    advice.scanned_projects = scanned_projects;
+    advice.eol_stale = eol_stale;
+    advice.eol_error = eol_error;
+    Ok(advice)
+}
+
+/// Version manager init lines from every detected shell's config files.
+async fn collect_profile_manager_inits() -> Vec<ProfileManagerInit> {
+    let shells = crate::core::terminal::detect_installed_shells()
+        .await
+        .unwrap_or_default();
+    let mut seen = HashSet::new();
+    let mut inits = Vec::new();
+    for file in shells.iter().flat_map(|shell| shell.config_files.iter()) {
+        if !file.exists || !seen.insert(file.path.clone()) {
+            continue;
+        }
+        let Ok(content) =
+            crate::core::terminal::read_shell_config(std::path::Path::new(&file.path)).await
+        else {
+            continue;
+        };
+        let lines: Vec<&str> = content.lines().collect();
+        for (line, manager) in crate::core::terminal::find_version_manager_inits(&content) {
+            inits.push(ProfileManagerInit {
+                manager: manager.to_string(),
+                path: file.path.clone(),
+                line,
+                content: lines[line - 1].to_string(),
+            });
+        }
+    }
+    inits
+}
+
+/// Explain which `env_type` binary a fresh shell and a Cognia launch run in `cwd`:
+/// PATH matches, Cognia shims, project pins, the global version and version
+/// managers initialized from shell profiles, with the winning step for each.
+#[tauri::command]
+pub async fn env_explain_resolution(
+    env_type: String,
+    cwd: String,
+    registry: State<'_, SharedRegistry>,
+    config: State<'_, crate::commands::config::SharedSettings>,
+) -> Result<EnvResolutionReport, String> {
+    let manager = EnvironmentManager::new(registry.inner().clone());
+    let logical = EnvironmentManager::logical_env_type(&env_type);
+    let cwd_path = std::path::Path::new(&cwd);
+    let detection = crate::provider::SystemEnvironmentType::all()
+        .into_iter()
+        .find(|system_type| system_type.env_type() == logical)
+        .map(|system_type| system_type.detection_config());
+
+    // A fresh shell searches its login PATH, which profiles may have changed
+    // from the one Cognia was started with.
+    let (shell, login_path) = path_shadow::login_shell_path().await;
+    let (search_path, path_warning) = match login_path {
+        Ok(path) => (std::ffi::OsString::from(path), None),
+        Err(error) => (
+            std::env::var_os("PATH").unwrap_or_default(),
+            Some(format!(
+                "Could not read the {} PATH, showing Cognia's own PATH instead: {}",
+                shell, error
+            )),
+        ),
+    };
+
+    // Prefer the first command name that actually exists on PATH (e.g. python3 vs python).
+    let commands: Vec<&str> = detection
+        .as_ref()
+        .map(|config| config.commands.clone())
+        .unwrap_or_else(|| vec![logical.as_str()]);
+    let (binary, found) = commands
+        .iter()
+        .map(|command| {
+            (
+                command.to_string(),
+                crate::platform::process::which_all_in(command, &search_path),
+            )
+        })
+        .find(|(_, found)| !found.is_empty())
+        .unwrap_or_else(|| (commands[0].to_string(), Vec::new()));
+
+    let mut path_matches = Vec::with_capacity(found.len());
+    for (idx, binary_path) in found.iter().enumerate() {
+        let binary_path = binary_path.display().to_string();
+        // Probing every shadowed copy is slow; the first few are what matters.
+        let version = if idx < 4 {
+            path_shadow::probe_binary_version(&binary_path, Some(&cwd), detection.as_ref()).await
+        } else {
+            None
+        };
+        path_matches.push(PathMatch {
+            dir: std::path::Path::new(&binary_path)
+                .parent()
+                .map(|dir| dir.display().to_string())
+                .unwrap_or_default(),
+            binary_path,
+            version,
+        });
+    }
+
+    let sources = enabled_detection_sources_for_env_type(&logical, config.inner()).await;
+    let pins = crate::core::project_env_detect::detect_env_version_candidates(
+        &logical, cwd_path, &sources,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    let cognia_version = manager
+        .detect_version_with_sources(&logical, cwd_path, &sources)
+        .await
+        .ok()
+        .flatten();
+
+    let global = match manager.resolve_provider(&logical, None, None).await {
+        Ok((_, provider_id, provider)) => provider
+            .get_current_version()
+            .await
+            .ok()
+            .flatten()
+            .map(|version| (provider_id, version)),
+        Err(_) => None,
+    };
+
+    let cognia_matches: Vec<String> = match &cognia_version {
+        Some(detected) => match manager
+            .get_env_modifications(&logical, &detected.version, None)
+            .await
+        {
+            Ok(mods) => std::env::join_paths(&mods.path_prepend)
+                .map(|joined| {
+                    crate::platform::process::which_all_in(&binary, &joined)
+                        .into_iter()
+                        .map(|path| path.display().to_string())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            Err(_) => Vec::new(),
+        },
+        None => Vec::new(),
+    };
+
+    let root_dir = config.read().await.get_root_dir();
+    let (shim_dir, shims) = match crate::core::ShimManager::new(&root_dir).await {
+        Ok(shim_manager) => (
+            Some(shim_manager.shim_dir().display().to_string()),
+            shim_manager
+                .list_shims()
+                .into_iter()
+                .filter(|shim| shim.binary_name == binary || shim.env_type == logical)
+                .cloned()
+                .collect(),
+        ),
+        Err(_) => (None, Vec::new()),
+    };
+
+    let manager_inits = collect_profile_manager_inits().await;
+
+    let mut report = env_resolution::build_resolution_report(&env_resolution::ResolutionInput {
+        env_type: &logical,
+        cwd: &cwd,
+        binary: &binary,
+        path_matches: &path_matches,
+        shim_dir: shim_dir.as_deref(),
+        shims: &shims,
+        pins: &pins,
+        global: global
+            .as_ref()
+            .map(|(provider_id, version)| (provider_id.as_str(), version.as_str())),
+        manager_inits: &manager_inits,
+        cognia_version: cognia_version.as_ref(),
+        cognia_matches: &cognia_matches,
+    });
+    report.warnings.extend(path_warning);
+    Ok(report)
+}
+
 // ──────────────────────────────────────────────────────
 // Environment version update checking & cleanup
 // ──────────────────────────────────────────────────────
@@ -2388,44 +3699,203 @@ pub async fn env_check_updates(
         .map_err(|e| e.to_string())
 }
 
-/// Check for updates across all known environment types
+/// Check for updates across all known environment types
+#[tauri::command]
+pub async fn env_check_updates_all(
+    registry: State<'_, SharedRegistry>,
+) -> Result<Vec<EnvUpdateCheckResult>, String> {
+    let manager = EnvironmentManager::new(registry.inner().clone());
+    manager
+        .check_all_env_updates()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Batch-remove old versions for an environment
+#[tauri::command]
+pub async fn env_cleanup_versions(
+    env_type: String,
+    versions_to_remove: Vec<String>,
+    registry: State<'_, SharedRegistry>,
+    config: State<'_, crate::commands::config::SharedSettings>,
+) -> Result<EnvCleanupResult, String> {
+    let manager = EnvironmentManager::new(registry.inner().clone());
+    let result = manager
+        .cleanup_versions(&env_type, &versions_to_remove)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // Invalidate environment caches after version cleanup
+    invalidate_env_caches(config.inner()).await;
+
+    Ok(result)
+}
+
+/// Check installed versions for partial or broken installs. Without an
+/// `env_type`, every available environment is checked.
+#[tauri::command]
+pub async fn env_validate_installs(
+    env_type: Option<String>,
+    registry: State<'_, SharedRegistry>,
+    config: State<'_, crate::commands::config::SharedSettings>,
+) -> Result<Vec<InstallValidation>, String> {
+    let manager = EnvironmentManager::new(registry.inner().clone());
+    if let Some(env_type) = env_type {
+        return manager
+            .validate_installs(&env_type)
+            .await
+            .map_err(|e| e.to_string());
+    }
+
+    let max_concurrency = config.read().await.startup.max_concurrent_scans;
+    let environments = manager
+        .list_environments_with_concurrency(max_concurrency)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut validations = Vec::new();
+    for env in environments.iter().filter(|env| env.available) {
+        if let Ok(found) = manager.validate_installs(&env.env_type).await {
+            validations.extend(found);
+        }
+    }
+    Ok(validations)
+}
+
+/// Remove broken installs together with their shims and `current` links.
+/// Only installs whose problems are conclusive are removed; the rest are
+/// reported in `errors`.
+#[tauri::command]
+pub async fn env_remove_broken(
+    env_type: String,
+    versions: Vec<String>,
+    registry: State<'_, SharedRegistry>,
+    config: State<'_, crate::commands::config::SharedSettings>,
+) -> Result<EnvCleanupResult, String> {
+    let root_dir = config.read().await.get_root_dir();
+    let manager = EnvironmentManager::new(registry.inner().clone());
+    let result = manager
+        .remove_broken_versions(&env_type, &versions, &root_dir)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    invalidate_env_caches(config.inner()).await;
+
+    Ok(result)
+}
+
+async fn load_env_disk_usage(
+    force: bool,
+    registry: &SharedRegistry,
+    config: &crate::commands::config::SharedSettings,
+) -> Result<EnvDiskUsageReport, String> {
+    let cache_key = "env:disk_usage";
+
+    if !force {
+        if let Ok(mut cache) = open_env_metadata_cache(config, ENV_DISK_USAGE_TTL).await {
+            if let Ok(Some(cached)) = cache.get::<EnvDiskUsageReport>(cache_key).await {
+                if !cached.is_stale {
+                    return Ok(cached.data);
+                }
+            }
+        }
+    }
+
+    let manager = EnvironmentManager::new(registry.clone());
+    let max_concurrency = config.read().await.startup.max_concurrent_scans;
+    let environments = manager
+        .list_environments_with_concurrency(max_concurrency)
+        .await
+        .map_err(|e| e.to_string())?;
+    let usage = EnvUsageLog::load().await.unwrap_or_default();
+    let report = env_disk_usage::compute_disk_usage(&environments, &usage).await;
+
+    if let Ok(mut cache) = open_env_metadata_cache(config, ENV_DISK_USAGE_TTL).await {
+        let _ = cache
+            .set_with_ttl(cache_key, &report, ENV_DISK_USAGE_TTL)
+            .await;
+    }
+
+    Ok(report)
+}
+
+/// Disk usage per environment type and installed version, with last-used
+/// timestamps from launch history. Cached; pass `force` to rescan.
 #[tauri::command]
-pub async fn env_check_updates_all(
+pub async fn env_disk_usage(
+    force: Option<bool>,
     registry: State<'_, SharedRegistry>,
-) -> Result<Vec<EnvUpdateCheckResult>, String> {
-    let manager = EnvironmentManager::new(registry.inner().clone());
-    manager
-        .check_all_env_updates()
-        .await
-        .map_err(|e| e.to_string())
+    config: State<'_, crate::commands::config::SharedSettings>,
+) -> Result<EnvDiskUsageReport, String> {
+    load_env_disk_usage(force.unwrap_or(false), registry.inner(), config.inner()).await
 }
 
-/// Batch-remove old versions for an environment
+/// Versions pinned by each project directory for the given environment types.
+async fn detect_project_pins(
+    manager: &EnvironmentManager,
+    project_dirs: &[String],
+    env_types: &[&str],
+) -> Vec<EnvProjectPin> {
+    let mut pins = Vec::new();
+    for dir in project_dirs {
+        for env_type in env_types {
+            if let Ok(Some(detected)) = manager
+                .detect_version(env_type, std::path::Path::new(dir))
+                .await
+            {
+                pins.push(EnvProjectPin {
+                    env_type: env_type.to_string(),
+                    version: detected.version,
+                    project_dir: dir.clone(),
+                    source: detected.source,
+                });
+            }
+        }
+    }
+    pins
+}
+
+/// Rank installed versions that no known project pins as removal candidates.
+/// Known projects are `project_dirs` plus working directories seen in launches.
 #[tauri::command]
-pub async fn env_cleanup_versions(
-    env_type: String,
-    versions_to_remove: Vec<String>,
+pub async fn env_reclaim_suggestions(
+    project_dirs: Option<Vec<String>>,
+    force: Option<bool>,
     registry: State<'_, SharedRegistry>,
     config: State<'_, crate::commands::config::SharedSettings>,
-) -> Result<EnvCleanupResult, String> {
-    let manager = EnvironmentManager::new(registry.inner().clone());
-    let result = manager
-        .cleanup_versions(&env_type, &versions_to_remove)
-        .await
-        .map_err(|e| e.to_string())?;
+) -> Result<EnvReclaimSuggestions, String> {
+    let report =
+        load_env_disk_usage(force.unwrap_or(false), registry.inner(), config.inner()).await?;
+    let usage = EnvUsageLog::load().await.unwrap_or_default();
+    let scanned_projects =
+        env_disk_usage::collect_project_dirs(&project_dirs.unwrap_or_default(), &usage);
 
-    // Invalidate environment caches after version cleanup
-    invalidate_env_caches(config.inner()).await;
-
-    Ok(result)
+    let manager = EnvironmentManager::new(registry.inner().clone());
+    let env_types: Vec<&str> = report
+        .environments
+        .iter()
+        .map(|env| env.env_type.as_str())
+        .collect();
+    let pins = detect_project_pins(&manager, &scanned_projects, &env_types).await;
+
+    let candidates = env_disk_usage::rank_reclaim_candidates(&report, &pins);
+    Ok(EnvReclaimSuggestions {
+        total_savings: candidates.iter().map(|c| c.estimated_savings).sum(),
+        candidates,
+        scanned_projects,
+        pins,
+        computed_at: report.computed_at,
+    })
 }
 
-/// List global packages installed under a specific environment version
+/// List global packages installed under a specific environment version.
+/// For Node, every installed package manager is asked unless
+/// `package_manager` picks one.
 #[tauri::command]
 pub async fn env_list_global_packages(
     env_type: String,
     version: String,
     provider_id: Option<String>,
+    package_manager: Option<String>,
     registry: State<'_, SharedRegistry>,
 ) -> Result<Vec<GlobalPackageInfo>, String> {
     let manager = EnvironmentManager::new(registry.inner().clone());
@@ -2439,9 +3909,10 @@ pub async fn env_list_global_packages(
         .map_err(|e| e.to_string())?;
 
     let logical = EnvironmentManager::logical_env_type(&env_type);
+    let package_manager = parse_package_manager(package_manager.as_deref())?;
 
     let packages = match logical.as_str() {
-        "node" => list_node_global_packages(&env_mods).await,
+        "node" => list_node_global_packages(&env_mods, package_manager).await,
         "python" => list_python_global_packages(&env_mods).await,
         "rust" => list_rust_global_packages(&env_mods).await,
         "go" => list_go_global_packages(&env_mods).await,
@@ -2451,19 +3922,70 @@ pub async fn env_list_global_packages(
     packages.map_err(|e| e.to_string())
 }
 
+/// Node package managers usable with a Node version, and the one used when
+/// an operation does not pick one
+#[tauri::command]
+pub async fn env_node_package_managers(
+    version: String,
+    provider_id: Option<String>,
+    project_path: Option<String>,
+    registry: State<'_, SharedRegistry>,
+) -> Result<NodePackageManagers, String> {
+    let manager = EnvironmentManager::new(registry.inner().clone());
+    let (_logical, _pid, provider) = manager
+        .resolve_provider("node", provider_id.as_deref(), Some(&version))
+        .await
+        .map_err(|e| e.to_string())?;
+    let env_mods = provider
+        .get_env_modifications(&version)
+        .map_err(|e| e.to_string())?;
+
+    let opts = build_process_opts(&env_mods, 30);
+    let available = node_globals::available_managers(&opts).await;
+    let preferred = node_globals::detect_preferred_manager(
+        project_path.as_deref().map(std::path::Path::new),
+        &available,
+    )
+    .await;
+    Ok(NodePackageManagers {
+        available,
+        preferred,
+    })
+}
+
 /// Migrate global packages from one version to another
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn env_migrate_packages(
     env_type: String,
-    _from_version: String,
+    from_version: String,
     to_version: String,
     packages: Vec<String>,
     provider_id: Option<String>,
+    package_manager: Option<String>,
     registry: State<'_, SharedRegistry>,
     config: State<'_, crate::commands::config::SharedSettings>,
     app: AppHandle,
 ) -> Result<EnvMigrateResult, String> {
     let manager = EnvironmentManager::new(registry.inner().clone());
+    let logical = EnvironmentManager::logical_env_type(&env_type);
+    let package_manager = parse_package_manager(package_manager.as_deref())?;
+
+    // Node globals are reinstalled with the manager that owns them under
+    // the source version, so look them up before switching
+    let mut node_origins = HashMap::new();
+    if logical == "node" && package_manager.is_none() {
+        let from_mods = manager
+            .resolve_provider(&env_type, provider_id.as_deref(), Some(&from_version))
+            .await
+            .ok()
+            .and_then(|(_logical, _pid, provider)| {
+                provider.get_env_modifications(&from_version).ok()
+            });
+        if let Some(from_mods) = from_mods {
+            node_origins = node_global_origins(&from_mods).await;
+        }
+    }
 
     // Switch to the target version first
     manager
@@ -2480,7 +4002,12 @@ pub async fn env_migrate_packages(
         .get_env_modifications(&to_version)
         .map_err(|e| e.to_string())?;
 
-    let logical = EnvironmentManager::logical_env_type(&env_type);
+    let fallback_manager = if logical == "node" && package_manager.is_none() {
+        let available = node_globals::available_managers(&build_process_opts(&env_mods, 30)).await;
+        node_globals::detect_preferred_manager(None, &available).await
+    } else {
+        None
+    };
 
     let mut migrated = Vec::new();
     let mut failed = Vec::new();
@@ -2500,7 +4027,13 @@ pub async fn env_migrate_packages(
         );
 
         let result = match logical.as_str() {
-            "node" => install_node_global_package(pkg, &env_mods).await,
+            "node" => {
+                let node_manager = package_manager
+                    .or_else(|| node_origins.get(pkg).copied())
+                    .or(fallback_manager)
+                    .unwrap_or(NodePackageManager::Npm);
+                install_node_global_package(node_manager, pkg, &env_mods).await
+            }
             "python" => install_python_global_package(pkg, &env_mods).await,
             "rust" => install_rust_global_package(pkg, &env_mods).await,
             "go" => install_go_global_package(pkg, &env_mods).await,
@@ -2529,11 +4062,106 @@ pub async fn env_migrate_packages(
     })
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+/// Update global packages of a Node version to their latest releases, each
+/// with the manager that installed it unless `package_manager` overrides it.
+/// Other environment types report every package as skipped.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn env_update_global_packages(
+    env_type: String,
+    version: String,
+    packages: Vec<String>,
+    provider_id: Option<String>,
+    package_manager: Option<String>,
+    registry: State<'_, SharedRegistry>,
+    config: State<'_, crate::commands::config::SharedSettings>,
+    app: AppHandle,
+) -> Result<EnvGlobalUpdateResult, String> {
+    let logical = EnvironmentManager::logical_env_type(&env_type);
+    if logical != "node" {
+        return Ok(EnvGlobalUpdateResult {
+            updated: vec![],
+            failed: vec![],
+            skipped: packages,
+        });
+    }
+    let package_manager = parse_package_manager(package_manager.as_deref())?;
+
+    let manager = EnvironmentManager::new(registry.inner().clone());
+    let (_logical, _pid, provider) = manager
+        .resolve_provider(&env_type, provider_id.as_deref(), Some(&version))
+        .await
+        .map_err(|e| e.to_string())?;
+    let env_mods = provider
+        .get_env_modifications(&version)
+        .map_err(|e| e.to_string())?;
+    let opts = build_process_opts(&env_mods, 30);
+    let origins = node_global_origins(&env_mods).await;
+
+    let mut updated = Vec::new();
+    let mut failed = Vec::new();
+    let mut skipped = Vec::new();
+
+    let total = packages.len();
+    for (idx, pkg) in packages.iter().enumerate() {
+        let _ = app.emit(
+            "env-global-update-progress",
+            serde_json::json!({
+                "envType": env_type,
+                "current": idx + 1,
+                "total": total,
+                "package": pkg,
+            }),
+        );
+
+        let Some(node_manager) = package_manager.or_else(|| origins.get(pkg).copied()) else {
+            skipped.push(pkg.clone());
+            continue;
+        };
+        match node_globals::update_global(node_manager, pkg, &opts).await {
+            Ok(()) => updated.push(pkg.clone()),
+            Err(e) => failed.push(MigrateFailure {
+                name: pkg.clone(),
+                error: e.to_string(),
+            }),
+        }
+    }
+
+    invalidate_env_caches(config.inner()).await;
+
+    Ok(EnvGlobalUpdateResult {
+        updated,
+        failed,
+        skipped,
+    })
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GlobalPackageInfo {
     pub name: String,
     pub version: String,
+    /// Executables the package provides, when known
+    #[serde(default)]
+    pub bins: Vec<String>,
+    /// Node package manager that installed the package
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_manager: Option<NodePackageManager>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodePackageManagers {
+    pub available: Vec<NodePackageManager>,
+    pub preferred: Option<NodePackageManager>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvGlobalUpdateResult {
+    pub updated: Vec<String>,
+    pub failed: Vec<MigrateFailure>,
+    pub skipped: Vec<String>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -2579,40 +4207,51 @@ fn build_process_opts(
     opts
 }
 
+fn parse_package_manager(name: Option<&str>) -> Result<Option<NodePackageManager>, String> {
+    name.filter(|name| !name.trim().is_empty())
+        .map(|name| {
+            NodePackageManager::parse(name)
+                .ok_or_else(|| format!("Unknown Node package manager: {}", name))
+        })
+        .transpose()
+}
+
 async fn list_node_global_packages(
     env_mods: &crate::platform::env::EnvModifications,
+    package_manager: Option<NodePackageManager>,
 ) -> Result<Vec<GlobalPackageInfo>, crate::error::CogniaError> {
-    use crate::platform::process;
-
     let opts = build_process_opts(env_mods, 30);
-    let output = process::execute("npm", &["ls", "-g", "--json", "--depth=0"], Some(opts)).await;
-
-    let stdout = match output {
-        Ok(o) => o.stdout,
-        Err(_) => return Ok(vec![]),
+    let packages = match package_manager {
+        Some(manager) => node_globals::list_globals(manager, &opts).await?,
+        None => {
+            let managers = node_globals::available_managers(&opts).await;
+            node_globals::list_all_globals(&managers, &opts).await
+        }
     };
 
-    let mut packages = Vec::new();
-    if let Ok(json) = serde_json::from_str::<serde_json::Value>(&stdout) {
-        if let Some(deps) = json.get("dependencies").and_then(|d| d.as_object()) {
-            for (name, info) in deps {
-                if name == "npm" || name == "corepack" {
-                    continue;
-                }
-                let version = info
-                    .get("version")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("unknown")
-                    .to_string();
-                packages.push(GlobalPackageInfo {
-                    name: name.clone(),
-                    version,
-                });
-            }
-        }
-    }
+    Ok(packages
+        .into_iter()
+        .map(|pkg| GlobalPackageInfo {
+            name: pkg.name,
+            version: pkg.version,
+            bins: pkg.bins,
+            package_manager: Some(pkg.manager),
+        })
+        .collect())
+}
 
-    Ok(packages)
+/// Which manager owns each global package of a Node version. A package
+/// installed by several managers is attributed to the first one listed.
+async fn node_global_origins(
+    env_mods: &crate::platform::env::EnvModifications,
+) -> HashMap<String, NodePackageManager> {
+    let opts = build_process_opts(env_mods, 30);
+    let managers = node_globals::available_managers(&opts).await;
+    let mut origins = HashMap::new();
+    for pkg in node_globals::list_all_globals(&managers, &opts).await {
+        origins.entry(pkg.name).or_insert(pkg.manager);
+    }
+    origins
 }
 
 async fn list_python_global_packages(
@@ -2665,7 +4304,11 @@ async fn list_python_global_packages(
                 continue;
             }
             if !name.is_empty() {
-                packages.push(GlobalPackageInfo { name, version });
+                packages.push(GlobalPackageInfo {
+                    name,
+                    version,
+                    ..Default::default()
+                });
             }
         }
     }
@@ -2674,23 +4317,12 @@ async fn list_python_global_packages(
 }
 
 async fn install_node_global_package(
+    manager: NodePackageManager,
     name: &str,
     env_mods: &crate::platform::env::EnvModifications,
 ) -> Result<(), crate::error::CogniaError> {
-    use crate::platform::process;
-
     let opts = build_process_opts(env_mods, 120);
-    match process::execute("npm", &["install", "-g", name], Some(opts)).await {
-        Ok(o) if o.success => Ok(()),
-        Ok(o) => Err(crate::error::CogniaError::Provider(format!(
-            "npm install -g {} failed: {}",
-            name, o.stderr
-        ))),
-        Err(e) => Err(crate::error::CogniaError::Provider(format!(
-            "Failed to install {}: {}",
-            name, e
-        ))),
-    }
+    node_globals::install_global(manager, name, &opts).await
 }
 
 async fn install_python_global_package(
@@ -2738,7 +4370,11 @@ async fn list_rust_global_packages(
     // Reuse the cargo provider's parsing logic
     let packages = parse_installed_list_output(&stdout)
         .into_iter()
-        .map(|(name, version)| GlobalPackageInfo { name, version })
+        .map(|(name, version)| GlobalPackageInfo {
+            name,
+            version,
+            ..Default::default()
+        })
         .collect();
 
     Ok(packages)
@@ -2818,6 +4454,7 @@ async fn list_go_global_packages(
                     packages.push(GlobalPackageInfo {
                         name: display_name.to_string(),
                         version: "installed".to_string(),
+                        ..Default::default()
                     });
                 }
             }
@@ -3690,6 +5327,129 @@ pub async fn rustup_set_profile(
     }
 }
 
+/// Disk usage of each installed Rust toolchain, broken down by component
+/// (rustc, std per target, docs), plus rustup's download cache
+#[tauri::command]
+pub async fn rustup_toolchain_sizes(
+    registry: State<'_, SharedRegistry>,
+) -> Result<crate::provider::rustup::RustToolchainSizeReport, String> {
+    let registry_guard = registry.read().await;
+    let provider = registry_guard
+        .get_environment_provider("rustup")
+        .ok_or("Rustup provider not found")?;
+
+    let rustup = provider
+        .as_any()
+        .downcast_ref::<crate::provider::rustup::RustupProvider>()
+        .ok_or("Failed to get RustupProvider")?;
+
+    rustup.toolchain_sizes().await.map_err(|e| e.to_string())
+}
+
+/// Remove docs from selected toolchains, uninstall old nightlies that no
+/// known project pins and prune rustup's download cache. With `dryRun` the
+/// planned actions and estimated savings are returned without changes.
+/// Known projects are `projectDirs` plus working directories seen in launches.
+#[tauri::command]
+pub async fn rustup_gc(
+    options: crate::provider::rustup::RustupGcOptions,
+    registry: State<'_, SharedRegistry>,
+    config: State<'_, crate::commands::config::SharedSettings>,
+) -> Result<crate::provider::rustup::RustupGcResult, String> {
+    use crate::provider::rustup::{
+        plan_rustup_gc, RustToolchainPin, RustupGcActionKind, RustupGcResult,
+    };
+
+    // Pin detection takes its own registry lock, so run it first
+    let usage = EnvUsageLog::load().await.unwrap_or_default();
+    let scanned_projects = env_disk_usage::collect_project_dirs(&options.project_dirs, &usage);
+    let pins: Vec<RustToolchainPin> = if options.nightly_older_than_days.is_some() {
+        let manager = EnvironmentManager::new(registry.inner().clone());
+        detect_project_pins(&manager, &scanned_projects, &["rust"])
+            .await
+            .into_iter()
+            .map(|pin| RustToolchainPin {
+                toolchain: pin.version,
+                project_dir: pin.project_dir,
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let registry_guard = registry.read().await;
+    let provider = registry_guard
+        .get_environment_provider("rustup")
+        .ok_or("Rustup provider not found")?;
+
+    let rustup = provider
+        .as_any()
+        .downcast_ref::<crate::provider::rustup::RustupProvider>()
+        .ok_or("Failed to get RustupProvider")?;
+
+    let report = rustup.toolchain_sizes().await.map_err(|e| e.to_string())?;
+    let protected: Vec<String> = match rustup.show_info().await {
+        Ok(info) => info
+            .default_toolchain
+            .into_iter()
+            .chain(info.active_toolchain)
+            .collect(),
+        // Without knowing the default toolchain no nightly is safe to remove
+        Err(e) if options.nightly_older_than_days.is_some() => {
+            return Err(format!("Cannot determine the default toolchain: {}", e))
+        }
+        Err(_) => Vec::new(),
+    };
+
+    let (mut actions, skipped) = plan_rustup_gc(
+        &report,
+        &options,
+        &pins,
+        &protected,
+        chrono::Utc::now().date_naive(),
+    );
+
+    let mut freed_bytes = 0;
+    if !options.dry_run {
+        for action in &mut actions {
+            let toolchain = action.toolchain.as_deref().unwrap_or_default();
+            let outcome = match action.kind {
+                RustupGcActionKind::RemoveDocs => rustup
+                    .remove_component("rust-docs", Some(toolchain))
+                    .await
+                    .map(|_| action.estimated_bytes),
+                RustupGcActionKind::UninstallToolchain => rustup
+                    .uninstall_toolchain(toolchain)
+                    .await
+                    .map(|_| action.estimated_bytes),
+                RustupGcActionKind::PruneDownloads => rustup.prune_downloads().await,
+            };
+            match outcome {
+                Ok(bytes) => {
+                    action.applied = true;
+                    freed_bytes += bytes;
+                }
+                Err(e) => action.error = Some(e.to_string()),
+            }
+        }
+        if actions
+            .iter()
+            .any(|action| action.applied && action.kind != RustupGcActionKind::PruneDownloads)
+        {
+            invalidate_env_caches(config.inner()).await;
+        }
+    }
+
+    Ok(RustupGcResult {
+        dry_run: options.dry_run,
+        estimated_savings: actions.iter().map(|action| action.estimated_bytes).sum(),
+        freed_bytes,
+        actions,
+        skipped,
+        scanned_projects,
+    })
+}
+
 // ──────────────────────────────────────────────────────
 // Go-specific commands: env info, mod tidy, cache mgmt
 // These use direct `go` CLI execution (no provider downcast needed)
@@ -3751,32 +5511,71 @@ pub async fn go_env_info() -> Result<crate::provider::goenv::GoEnvInfo, String>
     })
 }
 
-/// Run `go mod tidy` in a project directory (uses go mirror from settings)
+/// Resolve the directory to run a `go mod` command in.
+/// `module` selects a workspace module by its go.work `use` path or module path.
+async fn resolve_go_module_dir(project_path: &str, module: Option<&str>) -> Result<String, String> {
+    let module = match module.map(str::trim).filter(|m| !m.is_empty()) {
+        Some(module) => module,
+        None => return Ok(project_path.to_string()),
+    };
+    let info = crate::provider::goenv::read_go_work_info(std::path::Path::new(project_path))
+        .await
+        .map_err(|e| e.to_string())?;
+    let entry = crate::provider::goenv::resolve_go_work_module(&info, module)
+        .ok_or_else(|| format!("Module '{}' is not listed in {}", module, info.go_work_path))?;
+    if !entry.has_go_mod {
+        return Err(format!("Workspace module '{}' has no go.mod", entry.path));
+    }
+    Ok(entry.dir.clone())
+}
+
+/// Run `go mod tidy` in a project directory, or in one module of its go.work workspace
+/// (uses go mirror from settings)
 #[tauri::command]
 pub async fn go_mod_tidy(
     project_path: String,
+    module: Option<String>,
     config: State<'_, crate::commands::config::SharedSettings>,
 ) -> Result<String, String> {
+    let dir = resolve_go_module_dir(&project_path, module.as_deref()).await?;
     let go_mirror = config.read().await.get_mirror_url("go");
-    run_go_command(
-        &["mod", "tidy"],
-        Some(&project_path),
-        120,
-        go_mirror.as_deref(),
-    )
-    .await
+    run_go_command(&["mod", "tidy"], Some(&dir), 120, go_mirror.as_deref()).await
 }
 
-/// Run `go mod download` in a project directory (uses go mirror from settings)
+/// Run `go mod download` in a project directory, or in one module of its go.work workspace
+/// (uses go mirror from settings)
 #[tauri::command]
 pub async fn go_mod_download(
     project_path: String,
+    module: Option<String>,
+    config: State<'_, crate::commands::config::SharedSettings>,
+) -> Result<String, String> {
+    let dir = resolve_go_module_dir(&project_path, module.as_deref()).await?;
+    let go_mirror = config.read().await.get_mirror_url("go");
+    run_go_command(&["mod", "download"], Some(&dir), 300, go_mirror.as_deref()).await
+}
+
+/// Describe the go.work workspace governing `path` and its modules
+#[tauri::command]
+pub async fn go_work_info(path: String) -> Result<crate::provider::goenv::GoWorkInfo, String> {
+    crate::provider::goenv::read_go_work_info(std::path::Path::new(&path))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Run `go work sync` in the workspace governing `path` (uses go mirror from settings)
+#[tauri::command]
+pub async fn go_work_sync(
+    path: String,
     config: State<'_, crate::commands::config::SharedSettings>,
 ) -> Result<String, String> {
+    let info = crate::provider::goenv::read_go_work_info(std::path::Path::new(&path))
+        .await
+        .map_err(|e| e.to_string())?;
     let go_mirror = config.read().await.get_mirror_url("go");
     run_go_command(
-        &["mod", "download"],
-        Some(&project_path),
+        &["work", "sync"],
+        Some(&info.root),
         300,
         go_mirror.as_deref(),
     )
@@ -3831,3 +5630,12 @@ pub async fn go_cache_info() -> Result<crate::provider::goenv::GoCacheInfo, Stri
         mod_cache_size_human: crate::provider::goenv::go_format_bytes(mod_cache_size),
     })
 }
+
+/// List .NET runtimes visible to the active dotnet (`dotnet --list-runtimes`)
+#[tauri::command]
+pub async fn dotnet_list_runtimes() -> Result<Vec<crate::provider::dotnet::DotnetRuntime>, String> {
+    crate::provider::dotnet::DotnetProvider::new()
+        .list_runtimes()
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -7,7 +7,9 @@
 
 use crate::config::Settings;
 use crate::core::backup::{BackupDeleteResult, BackupManifest};
+use crate::core::path_shadow::{self, PathShadowReport};
 use crate::error::CogniaError;
+use crate::platform::dotenv::{self, DotenvLayer, DotenvProvenance, ResolvedDotenv};
 use crate::platform::env::{
     self, EnvFileFormat, EnvVarScope, EnvVarSensitivityReason, EnvVarValueSummary, ShellProfileInfo,
 };
@@ -188,6 +190,27 @@ pub struct EnvVarExportResult {
     pub revealed: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvFileResolution {
+    pub variables: HashMap<String, String>,
+    pub provenance: Vec<DotenvProvenance>,
+    pub loaded_files: Vec<String>,
+    pub missing_files: Vec<String>,
+    pub expanded: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvFileMergeExportResult {
+    pub content: String,
+    pub output_path: Option<String>,
+    pub variable_count: usize,
+    pub loaded_files: Vec<String>,
+    pub missing_files: Vec<String>,
+    pub expanded: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EnvVarActionSupport {
@@ -837,6 +860,23 @@ pub async fn envvar_remove_persistent(
     })
 }
 
+/// Apply several variable and PATH changes in one scope as a single transaction.
+/// Nothing is left half-applied: a failing step rolls back the earlier ones.
+#[tauri::command]
+pub async fn envvar_apply_batch(
+    ops: Vec<env::EnvVarOperation>,
+    scope: EnvVarScope,
+) -> Result<env::EnvVarTransactionResult, CogniaError> {
+    let support = build_action_support("apply_batch", Some(scope));
+    if !support.supported {
+        return Err(CogniaError::Config(support.reason));
+    }
+
+    env::EnvVarTransaction::with_operations(scope, ops)
+        .apply()
+        .await
+}
+
 #[tauri::command]
 pub async fn envvar_get_path(scope: EnvVarScope) -> Result<Vec<PathEntryInfo>, CogniaError> {
     let entries = env::get_persistent_path(scope).await?;
@@ -862,6 +902,16 @@ pub async fn envvar_get_path(scope: EnvVarScope) -> Result<Vec<PathEntryInfo>, C
     Ok(result)
 }
 
+/// For each managed binary (plus `binaries`), every PATH entry holding a copy
+/// and which copy wins, for both the app's PATH and a fresh login shell's.
+#[tauri::command]
+pub async fn path_shadow_report(
+    binaries: Option<Vec<String>>,
+) -> Result<PathShadowReport, CogniaError> {
+    let binaries = path_shadow::requested_binaries(binaries);
+    Ok(path_shadow::build_report(&binaries).await)
+}
+
 #[tauri::command]
 pub async fn envvar_add_path_entry(
     path: String,
@@ -1253,6 +1303,87 @@ pub async fn envvar_export_env_file(
     })
 }
 
+/// Load `.env` layers in order. Missing files are skipped (optional layers such
+/// as `.env.local` are common) and reported separately.
+async fn load_env_file_layers(
+    paths: &[String],
+) -> Result<(Vec<DotenvLayer>, Vec<String>, Vec<String>), CogniaError> {
+    let mut layers = Vec::with_capacity(paths.len());
+    let mut loaded = Vec::new();
+    let mut missing = Vec::new();
+
+    for path in paths {
+        let expanded = env::expand_path(path);
+        if !fs::exists(&expanded).await {
+            missing.push(path.clone());
+            continue;
+        }
+        let content = fs::read_file_string(&expanded).await?;
+        let entries = dotenv::parse_dotenv(&content).map_err(|e| match e {
+            CogniaError::Parse(message) => CogniaError::Parse(format!("{}: {}", path, message)),
+            other => other,
+        })?;
+        layers.push(DotenvLayer {
+            source: path.clone(),
+            entries,
+        });
+        loaded.push(path.clone());
+    }
+
+    Ok((layers, loaded, missing))
+}
+
+async fn resolve_env_file_layers(
+    paths: &[String],
+    expand: bool,
+) -> Result<(ResolvedDotenv, Vec<String>, Vec<String>), CogniaError> {
+    let (layers, loaded, missing) = load_env_file_layers(paths).await?;
+    let resolved = dotenv::resolve_layers(&layers, expand, &env::get_all_vars())?;
+    Ok((resolved, loaded, missing))
+}
+
+#[tauri::command]
+pub async fn envvar_resolve_env_files(
+    paths: Vec<String>,
+    expand: bool,
+) -> Result<EnvFileResolution, CogniaError> {
+    let (resolved, loaded_files, missing_files) = resolve_env_file_layers(&paths, expand).await?;
+
+    Ok(EnvFileResolution {
+        variables: resolved.variables(),
+        provenance: resolved.entries,
+        loaded_files,
+        missing_files,
+        expanded: expand,
+    })
+}
+
+#[tauri::command]
+pub async fn envvar_export_merged_env_file(
+    paths: Vec<String>,
+    expand: bool,
+    output_path: Option<String>,
+) -> Result<EnvFileMergeExportResult, CogniaError> {
+    let (resolved, loaded_files, missing_files) = resolve_env_file_layers(&paths, expand).await?;
+    let content = dotenv::render_dotenv(&resolved, expand);
+
+    let output_path = output_path
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty());
+    if let Some(path) = &output_path {
+        fs::write_file_atomic(env::expand_path(path), content.as_bytes()).await?;
+    }
+
+    Ok(EnvFileMergeExportResult {
+        variable_count: resolved.entries.len(),
+        content,
+        output_path,
+        loaded_files,
+        missing_files,
+        expanded: expand,
+    })
+}
+
 // ============================================================================
 // New commands
 // ============================================================================
@@ -1433,8 +1564,11 @@ pub async fn envvar_resolve_conflict(
 }
 
 #[tauri::command]
-pub fn envvar_expand(path: String) -> Result<String, CogniaError> {
-    Ok(env::expand_path(&path))
+pub fn envvar_expand(
+    path: String,
+    vars: Option<HashMap<String, String>>,
+) -> Result<String, CogniaError> {
+    Ok(env::expand_path_with(&path, &vars.unwrap_or_default()))
 }
 
 #[tauri::command]
@@ -2826,6 +2960,41 @@ fn sample_snapshot_payload(
         }
     }
 
+    #[tokio::test]
+    async fn resolve_env_files_layers_and_exports_merged_file() {
+        let dir = tempdir().unwrap();
+        let base = dir.path().join(".env");
+        let local = dir.path().join(".env.local");
+        let merged = dir.path().join("merged.env");
+        std::fs::write(&base, "HOST=localhost\nURL=\"http://${HOST}\"\n").unwrap();
+        std::fs::write(&local, "HOST='example.com'\n").unwrap();
+        let paths = vec![
+            base.display().to_string(),
+            local.display().to_string(),
+            dir.path().join(".env.production").display().to_string(),
+        ];
+
+        let resolution = envvar_resolve_env_files(paths.clone(), true).await.unwrap();
+        assert_eq!(resolution.variables["URL"], "http://example.com");
+        assert_eq!(resolution.loaded_files.len(), 2);
+        assert_eq!(resolution.missing_files, vec![paths[2].clone()]);
+        let host = resolution
+            .provenance
+            .iter()
+            .find(|entry| entry.key == "HOST")
+            .unwrap();
+        assert_eq!(host.source, paths[1]);
+        assert_eq!(host.overridden_sources, vec![paths[0].clone()]);
+
+        let export = envvar_export_merged_env_file(paths, true, Some(merged.display().to_string()))
+            .await
+            .unwrap();
+        assert_eq!(export.variable_count, 2);
+        let written = std::fs::read_to_string(&merged).unwrap();
+        assert_eq!(written, export.content);
+        assert!(written.contains("URL=http://example.com"));
+    }
+
     #[test]
     fn import_error_kind_maps_core_error_variants() {
         assert_eq!(
@@ -0,0 +1,101 @@
+use crate::provider::fvm::{FvmDoctorReport, FvmProjectStatus, FvmProvider, FvmRelease, FvmSdk};
+use crate::provider::install_script::InstallScript;
+use crate::provider::Provider;
+use std::path::PathBuf;
+
+async fn ensure_fvm(provider: &FvmProvider) -> Result<(), String> {
+    if provider.is_available().await {
+        Ok(())
+    } else {
+        Err("fvm is not installed or not on PATH (see fvm_self_install or https://fvm.app)".into())
+    }
+}
+
+#[tauri::command]
+pub async fn fvm_list_installed() -> Result<Vec<FvmSdk>, String> {
+    let provider = FvmProvider::new();
+    ensure_fvm(&provider).await?;
+    provider.list_sdks().await.map_err(|e| e.to_string())
+}
+
+/// Flutter releases, optionally limited to `stable`, `beta` or `dev`
+#[tauri::command]
+pub async fn fvm_list_releases(channel: Option<String>) -> Result<Vec<FvmRelease>, String> {
+    let provider = FvmProvider::new();
+    ensure_fvm(&provider).await?;
+    provider
+        .list_releases(channel.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Install a Flutter version or channel into the FVM cache
+#[tauri::command]
+pub async fn fvm_install(version: String) -> Result<String, String> {
+    let provider = FvmProvider::new();
+    ensure_fvm(&provider).await?;
+    provider
+        .install_sdk(&version)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Write the project's `.fvmrc`; `pin` freezes a channel at its current release
+#[tauri::command]
+pub async fn fvm_use(project_path: String, version: String, pin: bool) -> Result<String, String> {
+    let provider = FvmProvider::new();
+    ensure_fvm(&provider).await?;
+    provider
+        .use_sdk(&PathBuf::from(project_path), &version, pin)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn fvm_remove(version: String) -> Result<String, String> {
+    let provider = FvmProvider::new();
+    ensure_fvm(&provider).await?;
+    provider
+        .remove_sdk(&version)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn fvm_doctor() -> Result<FvmDoctorReport, String> {
+    let provider = FvmProvider::new();
+    ensure_fvm(&provider).await?;
+    provider.doctor().await.map_err(|e| e.to_string())
+}
+
+/// The project's pinned Flutter version or channel and whether it is installed
+#[tauri::command]
+pub async fn fvm_project_status(project_path: String) -> Result<FvmProjectStatus, String> {
+    Ok(FvmProvider::new()
+        .project_status(&PathBuf::from(project_path))
+        .await)
+}
+
+/// Download the standalone FVM install script so the user can review it
+/// and its SHA-256
+#[tauri::command]
+pub async fn fvm_install_script_preview() -> Result<InstallScript, String> {
+    FvmProvider::new()
+        .install_script_preview()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Install FVM through the detected Dart SDK or the standalone installer.
+/// The installer only runs if it matches the reviewed `expected_sha256`.
+#[tauri::command]
+pub async fn fvm_self_install(expected_sha256: Option<String>) -> Result<String, String> {
+    let provider = FvmProvider::new();
+    if provider.is_available().await {
+        return Err("fvm is already installed".into());
+    }
+    provider
+        .self_install(expected_sha256.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -20,9 +20,10 @@ fn clone_cancel() -> &'static Mutex<Option<watch::Sender<bool>>> {
     GitAheadBehind, GitBisectState, GitBlameEntry, GitBranchInfo, GitCloneOptions,
     GitCloneProgress, GitCommitDetail, GitCommitEntry, GitConfigEntry, GitContributor,
     GitDayActivity, GitDiffFile, GitFileStatEntry, GitGraphEntry, GitHookInfo, GitLfsFile,
-    GitMergeRebaseState, GitRebaseTodoItem, GitReflogEntry, GitRemoteInfo, GitRepoInfo,
-    GitRepoStats, GitStashEntry, GitStatusFile, GitSubmoduleInfo, GitSupportFeature,
-    GitSupportSnapshot, GitTagInfo, GitWorktreeInfo,
+    GitMergeRebaseState, GitRebaseExecuteOptions, GitRebasePlan, GitRebaseStatus,
+    GitRebaseTodoItem, GitReflogEntry, GitRemoteInfo, GitRepoInfo, GitRepoStats, GitStashEntry,
+    GitStatusFile, GitSubmoduleInfo, GitSupportFeature, GitSupportSnapshot, GitTagInfo,
+    GitWorktreeInfo,
 };
 
 /// Diff stats for a commit
@@ -1579,6 +1580,46 @@ pub async fn git_start_interactive_rebase(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn git_rebase_plan(path: String, base: String) -> Result<GitRebasePlan, String> {
+    get_provider()
+        .rebase_plan(&path, &base)
+        .await
+        .map_err(map_git_err)
+}
+
+#[tauri::command]
+pub async fn git_rebase_execute(
+    path: String,
+    plan: GitRebasePlan,
+    options: Option<GitRebaseExecuteOptions>,
+) -> Result<GitRebaseStatus, String> {
+    get_provider()
+        .rebase_execute(&path, &plan, &options.unwrap_or_default())
+        .await
+        .map_err(map_git_err)
+}
+
+#[tauri::command]
+pub async fn git_rebase_status(path: String) -> Result<GitRebaseStatus, String> {
+    get_provider()
+        .rebase_status(&path)
+        .await
+        .map_err(map_git_err)
+}
+
+#[tauri::command]
+pub async fn git_commit_amend(
+    path: String,
+    message: Option<String>,
+    no_edit: Option<bool>,
+) -> Result<String, String> {
+    get_provider()
+        .commit_amend(&path, message.as_deref(), no_edit.unwrap_or(false))
+        .await
+        .map_err(map_git_err)
+}
+
 // ============================================================================
 // Git bisect
 // ============================================================================
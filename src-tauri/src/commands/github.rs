@@ -4,17 +4,20 @@
     provider_secret_clear_internal, provider_secret_save_internal, provider_secret_status_internal,
     resolve_provider_secret, ProviderSecretStatus,
 };
+use crate::download::{checksum_discovery, AssetExplanation, AssetPicker, LibcType};
 use crate::platform::disk::format_size;
+use crate::platform::env::{Architecture, Platform};
 use crate::provider::github::{
     GitHubAsset, GitHubBranch, GitHubProvider, GitHubRelease, GitHubTag, GitHubWorkflowArtifact,
 };
+use crate::provider::rate_limit::{RateLimitPolicy, RateLimitStatus};
 use crate::SharedSecretVault;
 use serde::{Deserialize, Serialize};
-use tauri::State;
+use tauri::{AppHandle, State};
 
 use super::download::{
-    build_download_request_preset, download_add, DownloadRequest, DownloadRequestPreset,
-    SharedDownloadManager, SharedSettings,
+    apply_checksum_discovery, build_download_request_preset, download_add, DownloadRequest,
+    DownloadRequestPreset, SharedDownloadManager, SharedSettings,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +26,9 @@ pub struct BranchInfo {
     pub name: String,
     pub commit_sha: String,
     pub protected: bool,
+    /// Served from the conditional cache without using rate-limit quota
+    #[serde(default)]
+    pub from_cache: bool,
 }
 
 impl From<GitHubBranch> for BranchInfo {
@@ -31,6 +37,7 @@ fn from(b: GitHubBranch) -> Self {
             name: b.name,
             commit_sha: b.commit.sha,
             protected: b.protected,
+            from_cache: false,
         }
     }
 }
@@ -42,6 +49,9 @@ pub struct TagInfo {
     pub commit_sha: String,
     pub zipball_url: Option<String>,
     pub tarball_url: Option<String>,
+    /// Served from the conditional cache without using rate-limit quota
+    #[serde(default)]
+    pub from_cache: bool,
 }
 
 impl From<GitHubTag> for TagInfo {
@@ -51,6 +61,7 @@ fn from(t: GitHubTag) -> Self {
             commit_sha: t.commit.sha,
             zipball_url: t.zipball_url,
             tarball_url: t.tarball_url,
+            from_cache: false,
         }
     }
 }
@@ -66,6 +77,9 @@ pub struct ReleaseInfo {
     pub prerelease: bool,
     pub draft: bool,
     pub assets: Vec<AssetInfo>,
+    /// Served from the conditional cache without using rate-limit quota
+    #[serde(default)]
+    pub from_cache: bool,
 }
 
 impl From<GitHubRelease> for ReleaseInfo {
@@ -79,6 +93,7 @@ fn from(r: GitHubRelease) -> Self {
             prerelease: r.prerelease,
             draft: r.draft,
             assets: r.assets.into_iter().map(AssetInfo::from).collect(),
+            from_cache: false,
         }
     }
 }
@@ -93,6 +108,9 @@ pub struct AssetInfo {
     pub download_url: String,
     pub content_type: Option<String>,
     pub download_count: Option<u64>,
+    /// Asset the picker would choose for this system
+    #[serde(default)]
+    pub recommended: bool,
 }
 
 impl From<GitHubAsset> for AssetInfo {
@@ -105,10 +123,26 @@ fn from(a: GitHubAsset) -> Self {
             download_url: a.browser_download_url,
             content_type: a.content_type,
             download_count: a.download_count,
+            recommended: false,
         }
     }
 }
 
+/// Every release asset scored for this system, with the final choice
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetPickExplanation {
+    pub repo: String,
+    pub tag: String,
+    pub platform: Platform,
+    pub arch: Architecture,
+    pub libc: Option<LibcType>,
+    /// Number of user rules and exclusions in effect
+    pub user_rule_count: usize,
+    pub selected: Option<String>,
+    pub assets: Vec<AssetExplanation>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WorkflowArtifactInfo {
@@ -124,6 +158,9 @@ pub struct WorkflowArtifactInfo {
     pub workflow_run_number: Option<u64>,
     pub workflow_run_branch: Option<String>,
     pub workflow_run_head_sha: Option<String>,
+    /// Served from the conditional cache without using rate-limit quota
+    #[serde(default)]
+    pub from_cache: bool,
 }
 
 impl From<GitHubWorkflowArtifact> for WorkflowArtifactInfo {
@@ -150,6 +187,7 @@ fn from(artifact: GitHubWorkflowArtifact) -> Self {
                 .workflow_run
                 .as_ref()
                 .and_then(|run| run.head_sha.clone()),
+            from_cache: false,
         }
     }
 }
@@ -174,17 +212,30 @@ pub async fn github_parse_url(url: String) -> Result<Option<ParsedRepo>, String>
 }
 
 /// Helper to create a GitHubProvider with an optional explicit token.
-/// Resolution order: explicit input, unlocked secure storage, then env var fallback.
-async fn make_github_provider(
+/// Resolution order: explicit input, the secret store, unlocked secure storage,
+/// then env var fallback.
+/// Commands are interactive, so an exhausted rate limit fails right away
+/// instead of queueing until the reset.
+pub(crate) async fn make_github_provider(
     token: Option<String>,
     vault: &State<'_, SharedSecretVault>,
 ) -> GitHubProvider {
     let settings = crate::config::Settings::load().await.unwrap_or_default();
     let effective_token = {
         let vault_guard = vault.read().await;
-        resolve_provider_secret("github", token, &settings, &vault_guard)
+        resolve_provider_secret(
+            "github",
+            token,
+            &settings,
+            &vault_guard,
+            crate::platform::secrets::global(),
+        )
+        .await
     };
-    GitHubProvider::new().with_token(effective_token)
+    GitHubProvider::new()
+        .with_token(effective_token)
+        .with_cache_dir(Some(settings.get_cache_dir()))
+        .with_rate_limit_policy(RateLimitPolicy::FailFast)
 }
 
 fn build_github_download_request(
@@ -196,7 +247,7 @@ fn build_github_download_request(
     source_descriptor: crate::download::SourceDescriptor,
     artifact_profile: crate::download::ArtifactProfile,
 ) -> DownloadRequest {
-    build_download_request_preset(
+    let mut request = build_download_request_preset(
         url,
         destination,
         file_name,
@@ -208,7 +259,10 @@ fn build_github_download_request(
             artifact_profile: Some(artifact_profile),
             ..DownloadRequestPreset::default()
         },
-    )
+    );
+    // Archives and artifacts generated on demand never have a `.sha256` sibling
+    request.discover_checksum = Some(false);
+    request
 }
 
 fn infer_artifact_profile(
@@ -324,11 +378,18 @@ pub async fn github_list_branches(
     vault: State<'_, SharedSecretVault>,
 ) -> Result<Vec<BranchInfo>, String> {
     let provider = make_github_provider(token, &vault).await;
-    provider
+    let branches = provider
         .list_branches(&repo)
         .await
-        .map(|branches| branches.into_iter().map(BranchInfo::from).collect())
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    let from_cache = provider.last_response_from_cache();
+    Ok(branches
+        .into_iter()
+        .map(|branch| BranchInfo {
+            from_cache,
+            ..BranchInfo::from(branch)
+        })
+        .collect())
 }
 
 #[tauri::command]
@@ -338,11 +399,15 @@ pub async fn github_list_tags(
     vault: State<'_, SharedSecretVault>,
 ) -> Result<Vec<TagInfo>, String> {
     let provider = make_github_provider(token, &vault).await;
-    provider
-        .list_tags(&repo)
-        .await
-        .map(|tags| tags.into_iter().map(TagInfo::from).collect())
-        .map_err(|e| e.to_string())
+    let tags = provider.list_tags(&repo).await.map_err(|e| e.to_string())?;
+    let from_cache = provider.last_response_from_cache();
+    Ok(tags
+        .into_iter()
+        .map(|tag| TagInfo {
+            from_cache,
+            ..TagInfo::from(tag)
+        })
+        .collect())
 }
 
 #[tauri::command]
@@ -352,11 +417,18 @@ pub async fn github_list_releases(
     vault: State<'_, SharedSecretVault>,
 ) -> Result<Vec<ReleaseInfo>, String> {
     let provider = make_github_provider(token, &vault).await;
-    provider
+    let releases = provider
         .list_releases(&repo)
         .await
-        .map(|releases| releases.into_iter().map(ReleaseInfo::from).collect())
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    let from_cache = provider.last_response_from_cache();
+    Ok(releases
+        .into_iter()
+        .map(|release| ReleaseInfo {
+            from_cache,
+            ..ReleaseInfo::from(release)
+        })
+        .collect())
 }
 
 #[tauri::command]
@@ -366,16 +438,18 @@ pub async fn github_list_workflow_artifacts(
     vault: State<'_, SharedSecretVault>,
 ) -> Result<Vec<WorkflowArtifactInfo>, String> {
     let provider = make_github_provider(token, &vault).await;
-    provider
+    let artifacts = provider
         .list_workflow_artifacts(&repo)
         .await
-        .map(|artifacts| {
-            artifacts
-                .into_iter()
-                .map(WorkflowArtifactInfo::from)
-                .collect()
+        .map_err(|e| e.to_string())?;
+    let from_cache = provider.last_response_from_cache();
+    Ok(artifacts
+        .into_iter()
+        .map(|artifact| WorkflowArtifactInfo {
+            from_cache,
+            ..WorkflowArtifactInfo::from(artifact)
         })
-        .map_err(|e| e.to_string())
+        .collect())
 }
 
 #[tauri::command]
@@ -386,14 +460,59 @@ pub async fn github_get_release_assets(
     vault: State<'_, SharedSecretVault>,
 ) -> Result<Vec<AssetInfo>, String> {
     let provider = make_github_provider(token, &vault).await;
-    provider
+    let release = provider
         .get_release_by_tag(&repo, &tag)
         .await
-        .map(|release| release.assets.into_iter().map(AssetInfo::from).collect())
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    let recommended = AssetPicker::for_host()
+        .pick_best(&release.assets)
+        .map(|asset| asset.id);
+    Ok(release
+        .assets
+        .into_iter()
+        .map(|asset| {
+            let is_recommended = recommended == Some(asset.id);
+            AssetInfo {
+                recommended: is_recommended,
+                ..AssetInfo::from(asset)
+            }
+        })
+        .collect())
 }
 
+/// Explain how the asset picker scores a release's assets on this system
 #[tauri::command]
+pub async fn github_pick_asset_explain(
+    repo: String,
+    tag: String,
+    token: Option<String>,
+    vault: State<'_, SharedSecretVault>,
+) -> Result<AssetPickExplanation, String> {
+    let provider = make_github_provider(token, &vault).await;
+    let release = provider
+        .get_release_by_tag(&repo, &tag)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let picker = AssetPicker::for_host();
+    let assets = picker.explain(&release.assets);
+    Ok(AssetPickExplanation {
+        repo,
+        tag,
+        platform: picker.platform(),
+        arch: picker.arch(),
+        libc: picker.libc(),
+        user_rule_count: picker.rules().len(),
+        selected: assets
+            .iter()
+            .find(|asset| asset.selected)
+            .map(|asset| asset.name.clone()),
+        assets,
+    })
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn github_download_asset(
     repo: String,
     asset_id: u64,
@@ -401,12 +520,44 @@ pub async fn github_download_asset(
     asset_name: String,
     destination: String,
     token: Option<String>,
+    tag: Option<String>,
+    app: AppHandle,
     vault: State<'_, SharedSecretVault>,
     manager: State<'_, SharedDownloadManager>,
     settings: State<'_, SharedSettings>,
 ) -> Result<String, String> {
     let provider = make_github_provider(token, &vault).await;
 
+    // Checksums published next to the asset in the same release
+    let mut discovery = None;
+    if let Some(tag) = tag.filter(|tag| !tag.is_empty()) {
+        match provider.get_release_by_tag(&repo, &tag).await {
+            Ok(release) => {
+                let siblings: Vec<(String, String)> = release
+                    .assets
+                    .iter()
+                    .map(|asset| {
+                        let url = if provider.has_token() {
+                            provider.get_asset_api_download_url(&repo, asset.id)
+                        } else {
+                            asset.browser_download_url.clone()
+                        };
+                        (asset.name.clone(), url)
+                    })
+                    .collect();
+                discovery = Some(
+                    checksum_discovery::discover_from_release(
+                        &asset_name,
+                        &siblings,
+                        &provider.get_download_headers(),
+                    )
+                    .await,
+                );
+            }
+            Err(e) => log::warn!("Checksum discovery skipped for {}@{}: {}", repo, tag, e),
+        }
+    }
+
     // For authenticated requests, use the API URL for asset downloads
     let download_url = if provider.has_token() {
         provider.get_asset_api_download_url(&repo, asset_id)
@@ -425,7 +576,7 @@ pub async fn github_download_asset(
     };
     let artifact_profile =
         infer_artifact_profile(&asset_name, crate::download::SourceKind::GithubReleaseAsset);
-    let request = build_github_download_request(
+    let mut request = build_github_download_request(
         download_url,
         &destination,
         asset_name,
@@ -434,16 +585,24 @@ pub async fn github_download_asset(
         source_descriptor,
         artifact_profile,
     );
-    download_add(request, manager, settings).await
+    // Without a release to search, probe the public asset URL instead;
+    // API asset URLs have no `.sha256` sibling
+    request.discover_checksum = Some(discovery.is_none() && !provider.has_token());
+    if let Some(discovery) = discovery {
+        apply_checksum_discovery(&mut request, discovery);
+    }
+    download_add(app, request, manager, settings).await
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn github_download_source(
     repo: String,
     ref_name: String,
     format: String,
     destination: String,
     token: Option<String>,
+    app: AppHandle,
     vault: State<'_, SharedSecretVault>,
     manager: State<'_, SharedDownloadManager>,
     settings: State<'_, SharedSettings>,
@@ -479,16 +638,18 @@ pub async fn github_download_source(
         source_descriptor,
         artifact_profile,
     );
-    download_add(request, manager, settings).await
+    download_add(app, request, manager, settings).await
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn github_download_workflow_artifact(
     repo: String,
     artifact_id: u64,
     artifact_name: String,
     destination: String,
     token: Option<String>,
+    app: AppHandle,
     vault: State<'_, SharedSecretVault>,
     manager: State<'_, SharedDownloadManager>,
     settings: State<'_, SharedSettings>,
@@ -517,7 +678,7 @@ pub async fn github_download_workflow_artifact(
         source_descriptor,
         artifact_profile,
     );
-    download_add(request, manager, settings).await
+    download_add(app, request, manager, settings).await
 }
 
 #[tauri::command]
@@ -559,6 +720,9 @@ pub struct RepoInfoResponse {
     pub archived: bool,
     pub disabled: bool,
     pub topics: Vec<String>,
+    /// Served from the conditional cache without using rate-limit quota
+    #[serde(default)]
+    pub from_cache: bool,
 }
 
 #[tauri::command]
@@ -584,9 +748,33 @@ pub async fn github_get_repo_info(
         archived: info.archived.unwrap_or(false),
         disabled: info.disabled.unwrap_or(false),
         topics: info.topics.unwrap_or_default(),
+        from_cache: provider.last_response_from_cache(),
     })
 }
 
+/// Quota last reported by the GitHub API. Asks `/rate_limit`, which costs
+/// no quota, when nothing has been recorded yet or `refresh` is set.
+#[tauri::command]
+pub async fn github_rate_limit_status(
+    refresh: Option<bool>,
+    token: Option<String>,
+    vault: State<'_, SharedSecretVault>,
+) -> Result<RateLimitStatus, String> {
+    let provider = make_github_provider(token, &vault).await;
+    if provider.rate_limit_status().is_none() || refresh.unwrap_or(false) {
+        provider
+            .refresh_rate_limit()
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(provider
+        .rate_limit_status()
+        .unwrap_or_else(|| RateLimitStatus {
+            provider: "github".into(),
+            ..RateLimitStatus::default()
+        }))
+}
+
 #[tauri::command]
 pub async fn github_validate_token(token: String) -> Result<bool, String> {
     let provider = GitHubProvider::new().with_token(Some(token));
@@ -633,6 +821,7 @@ fn build_request_preserves_provider_headers_and_file_path() {
         assert!(request.delete_after_extract.is_none());
         assert!(request.auto_rename.is_none());
         assert!(request.tags.is_none());
+        assert_eq!(request.discover_checksum, Some(false));
         assert!(request.source_descriptor.is_some());
         assert!(request.artifact_profile.is_some());
         assert!(request.install_intent.is_some());
@@ -4,20 +4,25 @@
     provider_secret_clear_internal, provider_secret_save_internal, provider_secret_status_internal,
     resolve_provider_secret, ProviderSecretStatus,
 };
+use crate::download::{checksum_discovery, AssetPicker};
 use crate::error::CogniaError;
 use crate::provider::gitlab::{
     GitLabBranch, GitLabProvider, GitLabRelease, GitLabReleaseLink, GitLabTag,
 };
+use crate::provider::rate_limit::{RateLimitPolicy, RateLimitStatus};
 use crate::SharedSecretVault;
 use serde::{Deserialize, Serialize};
-use tauri::State;
+use tauri::{AppHandle, State};
 
 use super::download::{
-    build_download_request_preset, download_add, DownloadRequest, DownloadRequestPreset,
-    SharedDownloadManager, SharedSettings,
+    apply_checksum_discovery, build_download_request_preset, download_add, DownloadRequest,
+    DownloadRequestPreset, SharedDownloadManager, SharedSettings,
 };
 
 fn map_gitlab_error(e: CogniaError) -> String {
+    if matches!(e, CogniaError::RateLimited { .. }) {
+        return e.to_string();
+    }
     let msg = e.to_string();
     if msg.contains("401") || msg.contains("Unauthorized") {
         "GitLab authentication failed. Check your access token and ensure it has 'read_api' scope."
@@ -120,6 +125,9 @@ pub struct GitLabAssetInfo {
     pub url: String,
     pub direct_asset_url: Option<String>,
     pub link_type: Option<String>,
+    /// Asset the picker would choose for this system
+    #[serde(default)]
+    pub recommended: bool,
 }
 
 impl From<GitLabReleaseLink> for GitLabAssetInfo {
@@ -130,6 +138,7 @@ fn from(a: GitLabReleaseLink) -> Self {
             url: a.url,
             direct_asset_url: a.direct_asset_url,
             link_type: a.link_type,
+            recommended: false,
         }
     }
 }
@@ -186,6 +195,7 @@ pub async fn gitlab_parse_url(url: String) -> Result<Option<GitLabParsedProject>
 
 /// Helper to create a GitLabProvider with optional token and instance URL.
 /// Falls back to loading token and instance URL from settings if not provided.
+/// Like the GitHub commands, an exhausted rate limit fails right away.
 async fn make_gitlab_provider(
     token: Option<String>,
     instance_url: Option<String>,
@@ -195,7 +205,14 @@ async fn make_gitlab_provider(
 
     let effective_token = {
         let vault_guard = vault.read().await;
-        resolve_provider_secret("gitlab", token, &settings, &vault_guard)
+        resolve_provider_secret(
+            "gitlab",
+            token,
+            &settings,
+            &vault_guard,
+            crate::platform::secrets::global(),
+        )
+        .await
     };
 
     let effective_url = if instance_url.as_ref().map_or(true, |u| u.is_empty()) {
@@ -209,6 +226,8 @@ async fn make_gitlab_provider(
     GitLabProvider::new()
         .with_token(effective_token)
         .with_instance_url(effective_url)
+        .with_cache_dir(Some(settings.get_cache_dir()))
+        .with_rate_limit_policy(RateLimitPolicy::FailFast)
 }
 
 fn build_gitlab_download_request(
@@ -220,7 +239,7 @@ fn build_gitlab_download_request(
     source_descriptor: crate::download::SourceDescriptor,
     artifact_profile: crate::download::ArtifactProfile,
 ) -> DownloadRequest {
-    build_download_request_preset(
+    let mut request = build_download_request_preset(
         url,
         destination,
         file_name,
@@ -232,7 +251,10 @@ fn build_gitlab_download_request(
             artifact_profile: Some(artifact_profile),
             ..DownloadRequestPreset::default()
         },
-    )
+    );
+    // Archives and artifacts generated on demand never have a `.sha256` sibling
+    request.discover_checksum = Some(false);
+    request
 }
 
 fn infer_artifact_profile(
@@ -425,21 +447,29 @@ pub async fn gitlab_get_release_assets(
     vault: State<'_, SharedSecretVault>,
 ) -> Result<Vec<GitLabAssetInfo>, String> {
     let provider = make_gitlab_provider(token, instance_url, &vault).await;
-    provider
+    let release = provider
         .get_release_by_tag(&project, &tag)
         .await
-        .map(|release| {
-            release
-                .assets
-                .links
-                .into_iter()
-                .map(GitLabAssetInfo::from)
-                .collect()
+        .map_err(map_gitlab_error)?;
+    let recommended = AssetPicker::for_host()
+        .pick_best(&release.assets.links)
+        .map(|link| link.id);
+    Ok(release
+        .assets
+        .links
+        .into_iter()
+        .map(|link| {
+            let is_recommended = recommended == Some(link.id);
+            GitLabAssetInfo {
+                recommended: is_recommended,
+                ..GitLabAssetInfo::from(link)
+            }
         })
-        .map_err(map_gitlab_error)
+        .collect())
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn gitlab_download_asset(
     project: String,
     asset_url: String,
@@ -447,6 +477,8 @@ pub async fn gitlab_download_asset(
     destination: String,
     token: Option<String>,
     instance_url: Option<String>,
+    tag: Option<String>,
+    app: AppHandle,
     vault: State<'_, SharedSecretVault>,
     manager: State<'_, SharedDownloadManager>,
     settings: State<'_, SharedSettings>,
@@ -454,6 +486,30 @@ pub async fn gitlab_download_asset(
     let provider = make_gitlab_provider(token, instance_url, &vault).await;
 
     let headers = provider.get_download_headers();
+
+    // Checksums published next to the asset in the same release
+    let mut discovery = None;
+    if let Some(tag) = tag.filter(|tag| !tag.is_empty()) {
+        match provider.get_release_by_tag(&project, &tag).await {
+            Ok(release) => {
+                let siblings: Vec<(String, String)> = release
+                    .assets
+                    .links
+                    .iter()
+                    .map(|link| {
+                        let url = link.direct_asset_url.as_ref().unwrap_or(&link.url);
+                        (link.name.clone(), url.clone())
+                    })
+                    .collect();
+                discovery = Some(
+                    checksum_discovery::discover_from_release(&asset_name, &siblings, &headers)
+                        .await,
+                );
+            }
+            Err(e) => log::warn!("Checksum discovery skipped for {}@{}: {}", project, tag, e),
+        }
+    }
+
     let source_descriptor = crate::download::SourceDescriptor {
         kind: crate::download::SourceKind::GitlabReleaseAsset,
         provider: Some("gitlab".to_string()),
@@ -464,7 +520,7 @@ pub async fn gitlab_download_asset(
     };
     let artifact_profile =
         infer_artifact_profile(&asset_name, crate::download::SourceKind::GitlabReleaseAsset);
-    let request = build_gitlab_download_request(
+    let mut request = build_gitlab_download_request(
         asset_url,
         &destination,
         asset_name,
@@ -473,10 +529,16 @@ pub async fn gitlab_download_asset(
         source_descriptor,
         artifact_profile,
     );
-    download_add(request, manager, settings).await
+    // Without a release to search, probe the asset URL instead
+    request.discover_checksum = Some(discovery.is_none());
+    if let Some(discovery) = discovery {
+        apply_checksum_discovery(&mut request, discovery);
+    }
+    download_add(app, request, manager, settings).await
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn gitlab_download_source(
     project: String,
     ref_name: String,
@@ -484,6 +546,7 @@ pub async fn gitlab_download_source(
     destination: String,
     token: Option<String>,
     instance_url: Option<String>,
+    app: AppHandle,
     vault: State<'_, SharedSecretVault>,
     manager: State<'_, SharedDownloadManager>,
     settings: State<'_, SharedSettings>,
@@ -524,7 +587,7 @@ pub async fn gitlab_download_source(
         source_descriptor,
         artifact_profile,
     );
-    download_add(request, manager, settings).await
+    download_add(app, request, manager, settings).await
 }
 
 #[tauri::command]
@@ -705,6 +768,7 @@ pub async fn gitlab_list_pipeline_jobs(
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn gitlab_download_job_artifacts(
     project: String,
     job_id: u64,
@@ -712,6 +776,7 @@ pub async fn gitlab_download_job_artifacts(
     destination: String,
     token: Option<String>,
     instance_url: Option<String>,
+    app: AppHandle,
     vault: State<'_, SharedSecretVault>,
     manager: State<'_, SharedDownloadManager>,
     settings: State<'_, SharedSettings>,
@@ -742,7 +807,7 @@ pub async fn gitlab_download_job_artifacts(
         source_descriptor,
         artifact_profile,
     );
-    download_add(request, manager, settings).await
+    download_add(app, request, manager, settings).await
 }
 
 // ============================================================================
@@ -826,6 +891,7 @@ pub async fn gitlab_list_package_files(
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn gitlab_download_package_file(
     project: String,
     package_id: u64,
@@ -833,6 +899,7 @@ pub async fn gitlab_download_package_file(
     destination: String,
     token: Option<String>,
     instance_url: Option<String>,
+    app: AppHandle,
     vault: State<'_, SharedSecretVault>,
     manager: State<'_, SharedDownloadManager>,
     settings: State<'_, SharedSettings>,
@@ -861,7 +928,19 @@ pub async fn gitlab_download_package_file(
         source_descriptor,
         artifact_profile,
     );
-    download_add(request, manager, settings).await
+    download_add(app, request, manager, settings).await
+}
+
+/// Quota last reported by GitLab `RateLimit-*` headers for the instance and
+/// token, if any call has returned them yet
+#[tauri::command]
+pub async fn gitlab_rate_limit_status(
+    token: Option<String>,
+    instance_url: Option<String>,
+    vault: State<'_, SharedSecretVault>,
+) -> Result<Option<RateLimitStatus>, String> {
+    let provider = make_gitlab_provider(token, instance_url, &vault).await;
+    Ok(provider.rate_limit_status())
 }
 
 #[tauri::command]
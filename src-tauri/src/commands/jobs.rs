@@ -0,0 +1,36 @@
+use crate::core::operation_queue::{JobKind, JobQuery, JobRecord, JobStatus, SharedOperationQueue};
+use tauri::State;
+
+/// List running jobs followed by job history, newest first
+#[tauri::command]
+pub async fn jobs_list(
+    kind: Option<JobKind>,
+    status: Option<JobStatus>,
+    limit: Option<usize>,
+    jobs: State<'_, SharedOperationQueue>,
+) -> Result<Vec<JobRecord>, String> {
+    jobs.list(&JobQuery {
+        kind,
+        status,
+        limit,
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn jobs_get(
+    id: String,
+    jobs: State<'_, SharedOperationQueue>,
+) -> Result<Option<JobRecord>, String> {
+    jobs.get(&id).await.map_err(|e| e.to_string())
+}
+
+/// Request cancellation of a running job. Returns whether the job was running.
+#[tauri::command]
+pub async fn jobs_cancel(
+    id: String,
+    jobs: State<'_, SharedOperationQueue>,
+) -> Result<bool, String> {
+    Ok(jobs.cancel(&id))
+}
@@ -0,0 +1,13 @@
+use crate::provider::juliaup::JuliaupProvider;
+use crate::provider::Provider;
+
+/// Install juliaup through the Microsoft Store on Windows or the official
+/// install script elsewhere
+#[tauri::command]
+pub async fn juliaup_self_install() -> Result<String, String> {
+    let provider = JuliaupProvider::new();
+    if provider.is_available().await {
+        return Err("juliaup is already installed".into());
+    }
+    provider.self_install().await.map_err(|e| e.to_string())
+}
@@ -1,3 +1,9 @@
+use crate::config::Settings;
+use crate::core::env_disk_usage::EnvUsageLog;
+use crate::core::launch_monitor::{
+    LaunchLimits, LaunchStats, SharedLaunchMonitor, TrackedLaunchInfo,
+};
+use crate::core::shim::{self, ProgramMatch};
 use crate::core::EnvironmentManager;
 use crate::platform::env::ShellType;
 use crate::platform::process::{self, ProcessOptions, ProcessOutput};
@@ -12,6 +18,7 @@
 use uuid::Uuid;
 
 pub type SharedRegistry = Arc<RwLock<ProviderRegistry>>;
+pub type SharedSettings = Arc<RwLock<Settings>>;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -23,6 +30,10 @@ pub struct LaunchRequest {
     pub env_version: Option<String>,
     pub extra_env: Option<HashMap<String, String>>,
     pub timeout_secs: Option<u64>,
+    /// Label shown for the launch while it runs, such as the task that started it
+    pub profile: Option<String>,
+    /// Resource limits enforced while the launch runs
+    pub limits: Option<LaunchLimits>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -72,12 +83,46 @@ pub struct EnvInfo {
     pub env_vars: HashMap<String, String>,
 }
 
+/// Point the monitor at the configured sample interval and return a spawn
+/// hook that starts tracking the launch once its process is running.
+async fn track_on_spawn(
+    monitor: &SharedLaunchMonitor,
+    settings: &SharedSettings,
+    launch_id: &str,
+    request: &LaunchRequest,
+) -> impl FnOnce(Option<u32>) {
+    let interval_secs = settings.read().await.terminal.launch_sample_interval_secs;
+    monitor.set_sample_interval(Duration::from_secs(interval_secs as u64));
+
+    let monitor = monitor.clone();
+    let launch_id = launch_id.to_string();
+    let request = request.clone();
+    move |pid| {
+        let Some(pid) = pid else {
+            return;
+        };
+        monitor.track(TrackedLaunchInfo {
+            launch_id,
+            pid,
+            program: request.program,
+            args: request.args,
+            cwd: request.cwd,
+            profile: request.profile,
+            started_at: Utc::now().to_rfc3339(),
+            limits: request.limits,
+        });
+    }
+}
+
 /// Launch a program with a specific environment version
 #[tauri::command]
 pub async fn launch_with_env(
     request: LaunchRequest,
     registry: State<'_, SharedRegistry>,
+    monitor: State<'_, SharedLaunchMonitor>,
+    settings: State<'_, SharedSettings>,
 ) -> Result<LaunchResult, String> {
+    let launch_id = Uuid::new_v4().to_string();
     let mut opts = ProcessOptions::new();
 
     // Set working directory if specified
@@ -113,6 +158,12 @@ pub async fn launch_with_env(
             .get_env_modifications(env_type, &version, None)
             .await
             .map_err(|e| e.to_string())?;
+        EnvUsageLog::record(
+            env_type,
+            &version,
+            request.cwd.as_deref().map(std::path::Path::new),
+        )
+        .await;
 
         // Apply PATH modifications
         for path in &env_mods.path_prepend {
@@ -138,11 +189,11 @@ pub async fn launch_with_env(
 
     // Execute the program
     let args: Vec<&str> = request.args.iter().map(|s| s.as_str()).collect();
-    let output = process::execute(&request.program, &args, Some(opts))
-        .await
-        .map_err(|e| e.to_string())?;
+    let on_spawn = track_on_spawn(&monitor, &settings, &launch_id, &request).await;
+    let output = process::execute_tracked(&request.program, &args, Some(opts), on_spawn).await;
+    monitor.untrack(&launch_id);
 
-    Ok(output.into())
+    Ok(output.map_err(|e| e.to_string())?.into())
 }
 
 /// Launch a program and capture streaming output via Tauri events
@@ -151,6 +202,8 @@ pub async fn launch_with_streaming(
     request: LaunchRequest,
     app: AppHandle,
     registry: State<'_, SharedRegistry>,
+    monitor: State<'_, SharedLaunchMonitor>,
+    settings: State<'_, SharedSettings>,
 ) -> Result<LaunchResult, String> {
     let command_id = Uuid::new_v4().to_string();
     let mut opts = ProcessOptions::new();
@@ -188,6 +241,12 @@ pub async fn launch_with_streaming(
             .get_env_modifications(env_type, &version, None)
             .await
             .map_err(|e| e.to_string())?;
+        EnvUsageLog::record(
+            env_type,
+            &version,
+            request.cwd.as_deref().map(std::path::Path::new),
+        )
+        .await;
 
         // Apply PATH modifications
         for path in &env_mods.path_prepend {
@@ -218,10 +277,14 @@ pub async fn launch_with_streaming(
     let cmd_id_stdout = command_id.clone();
     let cmd_id_stderr = command_id.clone();
 
-    let output = process::execute_with_streaming(
+    // The command id doubles as the launch id, so output and resource
+    // events for the same process can be matched up
+    let on_spawn = track_on_spawn(&monitor, &settings, &command_id, &request).await;
+    let output = process::execute_with_streaming_tracked(
         &request.program,
         &args,
         Some(opts),
+        on_spawn,
         move |line| {
             let _ = app_handle_stdout.emit(
                 "command-output",
@@ -245,10 +308,42 @@ pub async fn launch_with_streaming(
             );
         },
     )
-    .await
-    .map_err(|e| e.to_string())?;
+    .await;
+    monitor.untrack(&command_id);
 
-    Ok(output.into())
+    Ok(output.map_err(|e| e.to_string())?.into())
+}
+
+/// List processes started via the launch commands that are still running
+#[tauri::command]
+pub async fn launch_list_running(
+    monitor: State<'_, SharedLaunchMonitor>,
+) -> Result<Vec<TrackedLaunchInfo>, String> {
+    Ok(monitor.list())
+}
+
+/// CPU, memory and child process count of a running launch's process tree
+#[tauri::command]
+pub async fn launch_get_stats(
+    launch_id: String,
+    monitor: State<'_, SharedLaunchMonitor>,
+) -> Result<LaunchStats, String> {
+    monitor.stats(&launch_id).await.map_err(|e| e.to_string())
+}
+
+/// Stop a running launch. Without `force` it is asked to exit first and only
+/// killed if it is still running after a grace period. Returns whether it
+/// exited without being killed.
+#[tauri::command]
+pub async fn launch_stop(
+    launch_id: String,
+    force: Option<bool>,
+    monitor: State<'_, SharedLaunchMonitor>,
+) -> Result<bool, String> {
+    monitor
+        .stop(&launch_id, force.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
 }
 
 /// Get shell activation script for a specific environment
@@ -291,6 +386,12 @@ pub async fn env_activate(
         .get_env_modifications(&env_type, &resolved_version, None)
         .await
         .map_err(|e| e.to_string())?;
+    EnvUsageLog::record(
+        &env_type,
+        &resolved_version,
+        project_path.as_deref().map(std::path::Path::new),
+    )
+    .await;
 
     // Determine shell type
     let shell_type = match shell.as_deref() {
@@ -415,6 +516,32 @@ pub async fn exec_shell_with_env(
     Ok(output.into())
 }
 
+/// Every match for a program on PATH, in PATH order, with its file type,
+/// version, the shim it belongs to and which match would actually run
+#[tauri::command]
+pub async fn which_program_detailed(
+    program: String,
+    settings: State<'_, SharedSettings>,
+) -> Result<Vec<ProgramMatch>, String> {
+    Ok(program_matches(&program, settings.inner()).await)
+}
+
+/// Matches for `program` on the process PATH, see [`shim::program_matches`]
+async fn program_matches(program: &str, settings: &SharedSettings) -> Vec<ProgramMatch> {
+    let root_dir = settings.read().await.get_root_dir();
+    let path_var = std::env::var_os("PATH").unwrap_or_default();
+    shim::program_matches(program, &path_var, &root_dir).await
+}
+
+/// The PATH match `which_program_detailed` reports as selected
+async fn selected_program(program: &str, settings: &SharedSettings) -> Option<String> {
+    program_matches(program, settings)
+        .await
+        .into_iter()
+        .find(|m| m.candidate.selected)
+        .map(|m| m.candidate.path)
+}
+
 /// Check which version of a program would be used
 #[tauri::command]
 pub async fn which_program(
@@ -423,10 +550,11 @@ pub async fn which_program(
     env_version: Option<String>,
     cwd: Option<String>,
     registry: State<'_, SharedRegistry>,
+    settings: State<'_, SharedSettings>,
 ) -> Result<Option<String>, String> {
-    // If no env_type, just use system which
+    // If no env_type, just use the PATH winner
     if env_type.is_none() {
-        return Ok(process::which(&program).await);
+        return Ok(selected_program(&program, settings.inner()).await);
     }
 
     let env_type = env_type.unwrap();
@@ -465,6 +593,6 @@ pub async fn which_program(
         }
     }
 
-    // Fallback to system which
-    Ok(process::which(&program).await)
+    // Fallback to the PATH winner
+    Ok(selected_program(&program, settings.inner()).await)
 }
@@ -0,0 +1,84 @@
+use crate::commands::config::SharedSettings;
+use crate::commands::environment::{detect_all_envs, detect_env, load_env_list};
+use crate::commands::shim::list_shim_infos;
+use crate::core::local_api::{self, LocalApiStatus, RpcError};
+use crate::SharedRegistry;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+
+#[derive(Deserialize)]
+struct DetectParams {
+    env_type: String,
+    path: String,
+}
+
+#[derive(Deserialize)]
+struct PathParams {
+    path: String,
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params).map_err(|e| RpcError::invalid_params(e.to_string()))
+}
+
+fn to_result<T: serde::Serialize>(outcome: Result<T, String>) -> Result<Value, RpcError> {
+    outcome
+        .and_then(|value| serde_json::to_value(value).map_err(|e| e.to_string()))
+        .map_err(RpcError::internal)
+}
+
+/// Serve one API method with the same helpers the Tauri commands use
+async fn dispatch(
+    method: &'static str,
+    params: Value,
+    registry: SharedRegistry,
+    settings: SharedSettings,
+) -> Result<Value, RpcError> {
+    match method {
+        "env.detect" => {
+            let p: DetectParams = parse_params(params)?;
+            to_result(detect_env(&p.env_type, &p.path, &registry, &settings).await)
+        }
+        "env.list" => to_result(load_env_list(false, &registry, &settings).await),
+        "project.pins" => {
+            let p: PathParams = parse_params(params)?;
+            to_result(detect_all_envs(&p.path, &registry, &settings).await)
+        }
+        "shims.list" => {
+            let root_dir = settings.read().await.get_root_dir();
+            to_result(list_shim_infos(&root_dir).await)
+        }
+        _ => Err(RpcError::internal(format!("Unhandled method: {}", method))),
+    }
+}
+
+/// Start or stop the local API server to match the current settings
+pub async fn apply_local_api_server(app: &AppHandle) {
+    let settings = app.state::<SharedSettings>().inner().clone();
+    let registry = app.state::<SharedRegistry>().inner().clone();
+
+    let (enabled, root_dir, rate_limit) = {
+        let s = settings.read().await;
+        (
+            s.general.local_api_enabled,
+            s.get_root_dir(),
+            s.general.local_api_rate_limit_per_minute,
+        )
+    };
+
+    let result = local_api::apply_server(enabled, &root_dir, rate_limit, move |method, params| {
+        dispatch(method, params, registry.clone(), settings.clone())
+    })
+    .await;
+    if let Err(e) = result {
+        log::warn!("{}", e);
+    }
+}
+
+/// Whether the local API is running, and where clients connect
+#[tauri::command]
+pub async fn api_server_status() -> Result<LocalApiStatus, String> {
+    Ok(local_api::status())
+}
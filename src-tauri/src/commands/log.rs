@@ -3,14 +3,17 @@
 use flate2::read::GzDecoder;
 use flate2::write::GzEncoder;
 use flate2::Compression;
+use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::io::{BufRead, Write};
 use std::path::PathBuf;
-use tauri::{AppHandle, Manager};
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
 use tokio::fs;
-use tokio::io::AsyncBufReadExt;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncSeekExt};
 
 const MAX_SCAN_LINES_LIMIT: usize = 200_000;
 
@@ -34,8 +37,6 @@ pub async fn log_export(
         return Err("Log file does not exist".to_string());
     }
 
-    let mut line_number = 0;
-
     let query_options = LogQueryOptions {
         file_name: options.file_name.clone(),
         level_filter: options.level_filter.clone(),
@@ -71,35 +72,12 @@ pub async fn log_export(
     };
     let mut first = true;
 
-    if is_gzip_log(&log_path) {
-        for (idx, line) in read_gzip_lines(&log_path).await?.into_iter().enumerate() {
-            line_number = idx + 1;
-            if let Some(entry) = parse_log_line(&line, line_number) {
-                if matches_filters(&entry, &query_options, regex.as_ref()) {
-                    append_export_entry(&mut content, &entry, export_format, &mut first);
-                }
-            }
+    for_each_log_entry(&log_path, |entry| {
+        if matches_filters(&entry, &query_options, regex.as_ref()) {
+            append_export_entry(&mut content, &entry, export_format, &mut first);
         }
-    } else {
-        let file = fs::File::open(&log_path)
-            .await
-            .map_err(|e| format!("Failed to open log file: {}", e))?;
-        let reader = tokio::io::BufReader::new(file);
-        let mut lines = reader.lines();
-
-        while let Some(line) = lines
-            .next_line()
-            .await
-            .map_err(|e| format!("Failed to read line: {}", e))?
-        {
-            line_number += 1;
-            if let Some(entry) = parse_log_line(&line, line_number) {
-                if matches_filters(&entry, &query_options, regex.as_ref()) {
-                    append_export_entry(&mut content, &entry, export_format, &mut first);
-                }
-            }
-        }
-    }
+    })
+    .await?;
 
     if export_format == LogExportFormat::Json {
         if first {
@@ -484,16 +462,27 @@ fn matches_filters(entry: &LogEntry, options: &LogQueryOptions, regex: Option<&R
 }
 
 fn parse_log_line(line: &str, line_number: usize) -> Option<LogEntry> {
-    // Parse structured log formats:
-    // 1) [TIMESTAMP][LEVEL][TARGET] MESSAGE
-    // 2) [DATE][TIME][TARGET][LEVEL] MESSAGE (legacy)
-    //
     // Fallback for unknown lines: treat entire line as INFO message.
     let line = line.trim();
     if line.is_empty() {
         return None;
     }
 
+    parse_structured_line(line, line_number).or_else(|| {
+        Some(LogEntry {
+            timestamp: String::new(),
+            level: "INFO".to_string(),
+            target: String::new(),
+            message: line.to_string(),
+            line_number,
+        })
+    })
+}
+
+fn parse_structured_line(line: &str, line_number: usize) -> Option<LogEntry> {
+    // Parse structured log formats:
+    // 1) [TIMESTAMP][LEVEL][TARGET] MESSAGE
+    // 2) [DATE][TIME][TARGET][LEVEL] MESSAGE (legacy)
     let mut parts: Vec<String> = Vec::new();
     let mut cursor = 0usize;
 
@@ -545,14 +534,43 @@ fn parse_log_line(line: &str, line_number: usize) -> Option<LogEntry> {
         }
     }
 
-    // Fallback: treat entire line as message
-    Some(LogEntry {
-        timestamp: String::new(),
-        level: "INFO".to_string(),
-        target: String::new(),
-        message: line.to_string(),
-        line_number,
-    })
+    None
+}
+
+/// Groups raw lines into records. Lines that don't start a structured record
+/// (panic messages, backtraces) are continuation lines of the record before
+/// them; with no record before them they stand alone.
+#[derive(Debug, Default)]
+struct LogRecordAssembler {
+    pending: Option<LogEntry>,
+}
+
+impl LogRecordAssembler {
+    /// Feed the next line. Returns a record once it is known to be complete.
+    fn push(&mut self, line: &str, line_number: usize) -> Option<LogEntry> {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            return None;
+        }
+
+        if let Some(entry) = parse_structured_line(trimmed, line_number) {
+            return self.pending.replace(entry);
+        }
+
+        match self.pending.as_mut() {
+            Some(pending) => {
+                pending.message.push('\n');
+                pending.message.push_str(line.trim_end());
+                None
+            }
+            None => parse_log_line(trimmed, line_number),
+        }
+    }
+
+    /// Take the record still waiting for continuation lines
+    fn finish(&mut self) -> Option<LogEntry> {
+        self.pending.take()
+    }
 }
 
 fn is_known_level(value: &str) -> bool {
@@ -616,6 +634,47 @@ pub async fn log_list_files(app: AppHandle) -> Result<Vec<LogFileInfo>, String>
     Ok(files)
 }
 
+/// Feed every record of a log file, gzip or plain, to `accept` in file order.
+/// Returns the number of lines read.
+async fn for_each_log_entry(
+    path: &std::path::Path,
+    mut accept: impl FnMut(LogEntry),
+) -> Result<usize, String> {
+    let mut records = LogRecordAssembler::default();
+    let mut line_number = 0usize;
+
+    if is_gzip_log(path) {
+        for line in read_gzip_lines(path).await? {
+            line_number += 1;
+            if let Some(entry) = records.push(&line, line_number) {
+                accept(entry);
+            }
+        }
+    } else {
+        let file = fs::File::open(path)
+            .await
+            .map_err(|e| format!("Failed to open log file: {}", e))?;
+        let reader = tokio::io::BufReader::new(file);
+        let mut lines = reader.lines();
+
+        while let Some(line) = lines
+            .next_line()
+            .await
+            .map_err(|e| format!("Failed to read line: {}", e))?
+        {
+            line_number += 1;
+            if let Some(entry) = records.push(&line, line_number) {
+                accept(entry);
+            }
+        }
+    }
+
+    if let Some(entry) = records.finish() {
+        accept(entry);
+    }
+    Ok(line_number)
+}
+
 /// Check whether the query has any active filters (level, search, time).
 fn has_active_filters(options: &LogQueryOptions) -> bool {
     if let Some(ref levels) = options.level_filter {
@@ -842,23 +901,35 @@ fn query_from_cached_lines(
             .map(|max_scan| lines.len().saturating_sub(max_scan))
             .unwrap_or(0);
 
+        let mut records = LogRecordAssembler::default();
+        let mut accept = |entry: LogEntry| {
+            if matches_filters(&entry, options, regex) {
+                total_count += 1;
+                push_window_entry(&mut window, need, entry);
+            }
+        };
         for (idx, line) in lines.iter().enumerate().skip(skip_lines) {
-            let line_number = idx + 1;
-            if let Some(entry) = parse_log_line(line, line_number) {
-                if matches_filters(&entry, options, regex) {
-                    total_count += 1;
-                    push_window_entry(&mut window, need, entry);
-                }
+            if let Some(entry) = records.push(line, idx + 1) {
+                accept(entry);
             }
         }
+        if let Some(entry) = records.finish() {
+            accept(entry);
+        }
     } else {
+        let mut records = LogRecordAssembler::default();
+        let mut accept = |entry: LogEntry| {
+            total_count += 1;
+            push_window_entry(&mut window, need, entry);
+        };
         for (idx, line) in lines.iter().enumerate() {
-            let line_number = idx + 1;
-            if let Some(entry) = parse_log_line(line, line_number) {
-                total_count += 1;
-                push_window_entry(&mut window, need, entry);
+            if let Some(entry) = records.push(line, idx + 1) {
+                accept(entry);
             }
         }
+        if let Some(entry) = records.finish() {
+            accept(entry);
+        }
     }
 
     let source_line_count = lines.len();
@@ -949,14 +1020,21 @@ pub async fn log_query(app: AppHandle, options: LogQueryOptions) -> Result<LogQu
             let source_line_count = tail_lines.total_lines;
             let scan_truncated = source_line_count > scanned_lines;
 
+            let mut records = LogRecordAssembler::default();
+            let mut accept = |entry: LogEntry| {
+                if matches_filters(&entry, &effective_options, regex.as_ref()) {
+                    total_count += 1;
+                    push_window_entry(&mut window, need, entry);
+                }
+            };
             for (line_number, line) in tail_lines.tail_lines {
-                if let Some(entry) = parse_log_line(&line, line_number) {
-                    if matches_filters(&entry, &effective_options, regex.as_ref()) {
-                        total_count += 1;
-                        push_window_entry(&mut window, need, entry);
-                    }
+                if let Some(entry) = records.push(&line, line_number) {
+                    accept(entry);
                 }
             }
+            if let Some(entry) = records.finish() {
+                accept(entry);
+            }
 
             return Ok(build_query_result_from_window(
                 &window,
@@ -979,28 +1057,14 @@ pub async fn log_query(app: AppHandle, options: LogQueryOptions) -> Result<LogQu
         let need = offset.saturating_add(limit);
         let mut window: VecDeque<LogEntry> = VecDeque::with_capacity(need.saturating_add(1));
         let mut total_count = 0usize;
-        let file = fs::File::open(&log_path)
-            .await
-            .map_err(|e| format!("Failed to open log file: {}", e))?;
-        let reader = tokio::io::BufReader::new(file);
-        let mut lines = reader.lines();
-        let mut line_number = 0usize;
-
-        while let Some(line) = lines
-            .next_line()
-            .await
-            .map_err(|e| format!("Failed to read line: {}", e))?
-        {
-            line_number += 1;
-            if let Some(entry) = parse_log_line(&line, line_number) {
-                if matches_filters(&entry, &effective_options, regex.as_ref()) {
-                    total_count += 1;
-                    push_window_entry(&mut window, need, entry);
-                }
+        let source_line_count = for_each_log_entry(&log_path, |entry| {
+            if matches_filters(&entry, &effective_options, regex.as_ref()) {
+                total_count += 1;
+                push_window_entry(&mut window, need, entry);
             }
-        }
+        })
+        .await?;
 
-        let source_line_count = line_number;
         Ok(build_query_result_from_window(
             &window,
             total_count,
@@ -1020,26 +1084,12 @@ pub async fn log_query(app: AppHandle, options: LogQueryOptions) -> Result<LogQu
         let need = offset.saturating_add(limit);
         let mut ring: VecDeque<LogEntry> = VecDeque::with_capacity(need.saturating_add(1));
         let mut total_count = 0usize;
-        let file = fs::File::open(&log_path)
-            .await
-            .map_err(|e| format!("Failed to open log file: {}", e))?;
-        let reader = tokio::io::BufReader::new(file);
-        let mut lines = reader.lines();
-        let mut line_number = 0usize;
-
-        while let Some(line) = lines
-            .next_line()
-            .await
-            .map_err(|e| format!("Failed to read line: {}", e))?
-        {
-            line_number += 1;
-            if let Some(entry) = parse_log_line(&line, line_number) {
-                total_count += 1;
-                push_window_entry(&mut ring, need, entry);
-            }
-        }
+        let source_line_count = for_each_log_entry(&log_path, |entry| {
+            total_count += 1;
+            push_window_entry(&mut ring, need, entry);
+        })
+        .await?;
 
-        let source_line_count = line_number;
         Ok(build_query_result_from_window(
             &ring,
             total_count,
@@ -1056,6 +1106,229 @@ pub async fn log_query(app: AppHandle, options: LogQueryOptions) -> Result<LogQu
     }
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogStatsOptions {
+    pub file_name: Option<String>,
+    pub start_time: Option<i64>,
+    pub end_time: Option<i64>,
+}
+
+/// Per-level and per-target record counts for the log panel's summary bar
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogStats {
+    pub total_count: usize,
+    pub by_level: BTreeMap<String, usize>,
+    pub by_target: BTreeMap<String, usize>,
+    pub first_timestamp: Option<String>,
+    pub last_timestamp: Option<String>,
+}
+
+impl LogStats {
+    fn record(&mut self, entry: &LogEntry) {
+        self.total_count += 1;
+        *self.by_level.entry(entry.level.clone()).or_default() += 1;
+        if !entry.target.is_empty() {
+            *self.by_target.entry(entry.target.clone()).or_default() += 1;
+        }
+        if !entry.timestamp.is_empty() {
+            if self.first_timestamp.is_none() {
+                self.first_timestamp = Some(entry.timestamp.clone());
+            }
+            self.last_timestamp = Some(entry.timestamp.clone());
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn log_stats(app: AppHandle, options: LogStatsOptions) -> Result<LogStats, String> {
+    let mut stats = LogStats::default();
+    let log_path = match resolve_log_path(&app, &options.file_name).await {
+        Ok(path) if path.exists() => path,
+        _ => return Ok(stats),
+    };
+
+    let range = LogQueryOptions {
+        file_name: options.file_name,
+        level_filter: None,
+        target: None,
+        search: None,
+        use_regex: None,
+        start_time: options.start_time,
+        end_time: options.end_time,
+        limit: None,
+        offset: None,
+        max_scan_lines: None,
+    };
+    for_each_log_entry(&log_path, |entry| {
+        if matches_filters(&entry, &range, None) {
+            stats.record(&entry);
+        }
+    })
+    .await?;
+    Ok(stats)
+}
+
+/// How often a followed log file is checked for new lines
+const LOG_FOLLOW_POLL: Duration = Duration::from_millis(500);
+
+static LOG_FOLLOWERS: Lazy<StdMutex<HashMap<String, tauri::async_runtime::JoinHandle<()>>>> =
+    Lazy::new(|| StdMutex::new(HashMap::new()));
+
+/// New matching records of a followed log file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogFollowBatch {
+    pub follow_id: String,
+    pub entries: Vec<LogEntry>,
+}
+
+/// New lines appended to a log file since the last poll, split into records
+struct LogFollower {
+    path: PathBuf,
+    position: u64,
+    line_number: usize,
+    partial: Vec<u8>,
+    records: LogRecordAssembler,
+}
+
+impl LogFollower {
+    /// Start at the current end of `path`
+    async fn new(path: PathBuf) -> Result<Self, String> {
+        let mut file = fs::File::open(&path)
+            .await
+            .map_err(|e| format!("Failed to open log file: {}", e))?;
+        let mut buf = vec![0u8; 64 * 1024];
+        let mut position = 0u64;
+        let mut line_number = 0usize;
+        loop {
+            let read = file
+                .read(&mut buf)
+                .await
+                .map_err(|e| format!("Failed to read log file: {}", e))?;
+            if read == 0 {
+                break;
+            }
+            position += read as u64;
+            line_number += buf[..read].iter().filter(|b| **b == b'\n').count();
+        }
+        Ok(Self {
+            path,
+            position,
+            line_number,
+            partial: Vec::new(),
+            records: LogRecordAssembler::default(),
+        })
+    }
+
+    /// Records completed since the last poll. A record still waiting for
+    /// continuation lines is released once a poll finds nothing new.
+    async fn poll(&mut self) -> Result<Vec<LogEntry>, String> {
+        let len = fs::metadata(&self.path)
+            .await
+            .map_err(|e| format!("Failed to read log file: {}", e))?
+            .len();
+        if len < self.position {
+            // Cleared or rotated in place: start over from the top
+            self.position = 0;
+            self.line_number = 0;
+            self.partial.clear();
+            self.records = LogRecordAssembler::default();
+        }
+
+        let mut entries = Vec::new();
+        if len == self.position {
+            entries.extend(self.records.finish());
+            return Ok(entries);
+        }
+
+        let mut file = fs::File::open(&self.path)
+            .await
+            .map_err(|e| format!("Failed to open log file: {}", e))?;
+        file.seek(std::io::SeekFrom::Start(self.position))
+            .await
+            .map_err(|e| format!("Failed to read log file: {}", e))?;
+        let read = file
+            .read_to_end(&mut self.partial)
+            .await
+            .map_err(|e| format!("Failed to read log file: {}", e))?;
+        self.position += read as u64;
+
+        while let Some(newline) = self.partial.iter().position(|b| *b == b'\n') {
+            let raw: Vec<u8> = self.partial.drain(..=newline).collect();
+            let line = String::from_utf8_lossy(&raw);
+            self.line_number += 1;
+            entries.extend(
+                self.records
+                    .push(line.trim_end_matches(['\r', '\n']), self.line_number),
+            );
+        }
+        Ok(entries)
+    }
+}
+
+/// Tail a log file (the newest one unless `fileName` is set) and emit
+/// records matching the query filters as `log-follow-entries` events.
+/// Returns the id to pass to `log_follow_stop`.
+#[tauri::command]
+pub async fn log_follow_start(app: AppHandle, options: LogQueryOptions) -> Result<String, String> {
+    let log_path = resolve_log_path(&app, &options.file_name).await?;
+    let mut follower = LogFollower::new(log_path).await?;
+    let regex = build_search_regex(&options.search, options.use_regex.unwrap_or(false));
+    let follow_id = uuid::Uuid::new_v4().to_string();
+
+    let task_id = follow_id.clone();
+    let handle = tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(LOG_FOLLOW_POLL);
+        loop {
+            interval.tick().await;
+            let entries = match follower.poll().await {
+                Ok(entries) => entries,
+                Err(e) => {
+                    log::debug!("Log follow {} poll failed: {}", task_id, e);
+                    continue;
+                }
+            };
+            let entries: Vec<_> = entries
+                .into_iter()
+                .filter(|entry| matches_filters(entry, &options, regex.as_ref()))
+                .collect();
+            if !entries.is_empty() {
+                let _ = app.emit(
+                    "log-follow-entries",
+                    LogFollowBatch {
+                        follow_id: task_id.clone(),
+                        entries,
+                    },
+                );
+            }
+        }
+    });
+
+    LOG_FOLLOWERS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(follow_id.clone(), handle);
+    Ok(follow_id)
+}
+
+/// Stop a follow started by `log_follow_start`. Returns false for unknown ids.
+#[tauri::command]
+pub async fn log_follow_stop(follow_id: String) -> Result<bool, String> {
+    let handle = LOG_FOLLOWERS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&follow_id);
+    match handle {
+        Some(handle) => {
+            handle.abort();
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
 #[tauri::command]
 pub async fn log_clear(
     app: AppHandle,
@@ -1731,6 +2004,95 @@ fn fallback_parse_for_unstructured_line() {
         assert_eq!(parsed.message, line);
     }
 
+    #[test]
+    fn continuation_lines_attach_to_preceding_record() {
+        let lines = [
+            "thread 'main' panicked before any record",
+            "[2026-01-01 10:00:00][ERROR][cognia::core] task failed",
+            "   0: std::panicking::begin_panic",
+            "",
+            "   1: cognia::core::run",
+            "[2026-01-01 10:00:01][INFO][cognia::app] recovered",
+        ];
+        let mut records = LogRecordAssembler::default();
+        let mut entries: Vec<LogEntry> = lines
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, line)| records.push(line, idx + 1))
+            .collect();
+        entries.extend(records.finish());
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].level, "INFO");
+        assert_eq!(entries[1].line_number, 2);
+        assert_eq!(
+            entries[1].message,
+            "task failed\n   0: std::panicking::begin_panic\n   1: cognia::core::run"
+        );
+        assert_eq!(entries[2].message, "recovered");
+    }
+
+    #[test]
+    fn stats_count_levels_and_targets() {
+        let mut stats = LogStats::default();
+        for line in [
+            "[2026-01-01 10:00:00][INFO][cognia::app] a",
+            "[2026-01-01 10:00:01][WARN][cognia::app] b",
+            "[2026-01-01 10:00:02][INFO][cognia::core] c",
+            "unstructured",
+        ] {
+            stats.record(&parse_log_line(line, 1).unwrap());
+        }
+
+        assert_eq!(stats.total_count, 4);
+        assert_eq!(stats.by_level.get("INFO"), Some(&3));
+        assert_eq!(stats.by_level.get("WARN"), Some(&1));
+        assert_eq!(stats.by_target.get("cognia::app"), Some(&2));
+        assert_eq!(stats.by_target.len(), 2);
+        assert_eq!(
+            stats.first_timestamp.as_deref(),
+            Some("2026-01-01 10:00:00")
+        );
+        assert_eq!(stats.last_timestamp.as_deref(), Some("2026-01-01 10:00:02"));
+    }
+
+    #[tokio::test]
+    async fn follower_reads_only_appended_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, "[2026-01-01 10:00:00][INFO][app] old\n").unwrap();
+
+        let mut follower = LogFollower::new(path.clone()).await.unwrap();
+        assert!(follower.poll().await.unwrap().is_empty());
+
+        let append = |text: &str| {
+            let mut file = std::fs::OpenOptions::new()
+                .append(true)
+                .open(&path)
+                .unwrap();
+            file.write_all(text.as_bytes()).unwrap();
+        };
+        append("[2026-01-01 10:00:01][ERROR][app] boom\n  at frame\n[2026-01-01 10:00:02][IN");
+        // The record stays open until the next one starts
+        assert!(follower.poll().await.unwrap().is_empty());
+
+        append("FO][app] next\n");
+        let entries = follower.poll().await.unwrap();
+        assert_eq!(collect_messages(&entries), vec!["boom\n  at frame"]);
+        assert_eq!(entries[0].line_number, 2);
+        // Nothing new: the waiting record is released
+        assert_eq!(
+            collect_messages(&follower.poll().await.unwrap()),
+            vec!["next"]
+        );
+
+        std::fs::write(&path, "[2026-01-01 11:00:00][INFO][app] fresh\n").unwrap();
+        follower.poll().await.unwrap();
+        let entries = follower.poll().await.unwrap();
+        assert_eq!(collect_messages(&entries), vec!["fresh"]);
+        assert_eq!(entries[0].line_number, 1);
+    }
+
     #[test]
     fn read_gzip_lines_sync_reads_lines() {
         let unique = std::time::SystemTime::now()
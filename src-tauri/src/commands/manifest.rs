@@ -1,6 +1,12 @@
+use crate::config::manifest::{
+    diagnose_manifest_file, format_manifest_file, DiagnosticContext, ManifestDiagnostics,
+    ManifestFormatResult, TomlManifest, TOML_MANIFEST_FILENAME,
+};
+use crate::SharedRegistry;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use tauri::State;
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
 
@@ -13,30 +19,12 @@ pub struct ManifestInfo {
     pub path: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
-struct CogniaManifest {
-    #[serde(default)]
-    project: ProjectInfo,
-    #[serde(default)]
-    environments: HashMap<String, String>,
-    #[serde(default)]
-    packages: Vec<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize, Default)]
-struct ProjectInfo {
-    name: Option<String>,
-    version: Option<String>,
-}
-
-const MANIFEST_FILE_NAME: &str = "cognia.toml";
-
 /// Find manifest file by searching upward from the given path
 async fn find_manifest(start_path: &std::path::Path) -> Option<PathBuf> {
     let mut current = start_path.to_path_buf();
 
     loop {
-        let manifest_path = current.join(MANIFEST_FILE_NAME);
+        let manifest_path = current.join(TOML_MANIFEST_FILENAME);
         if manifest_path.exists() {
             return Some(manifest_path);
         }
@@ -87,7 +75,7 @@ pub async fn manifest_read(project_path: Option<String>) -> Result<Option<Manife
         .await
         .map_err(|e| format!("Failed to read manifest: {}", e))?;
 
-    let manifest: CogniaManifest =
+    let manifest: TomlManifest =
         toml::from_str(&content).map_err(|e| format!("Failed to parse manifest: {}", e))?;
 
     Ok(Some(ManifestInfo {
@@ -106,7 +94,7 @@ pub async fn manifest_init(project_path: Option<String>) -> Result<(), String> {
         None => get_default_project_path(),
     };
 
-    let manifest_path = target_path.join(MANIFEST_FILE_NAME);
+    let manifest_path = target_path.join(TOML_MANIFEST_FILENAME);
 
     // Check if manifest already exists
     if manifest_path.exists() {
@@ -142,3 +130,36 @@ pub async fn manifest_init(project_path: Option<String>) -> Result<(), String> {
 
     Ok(())
 }
+
+/// Diagnoses a `cognia.toml` or `CogniaLauncher.yaml` manifest, including
+/// drift against its lockfile.
+#[tauri::command]
+pub async fn manifest_diagnostics(
+    path: String,
+    registry: State<'_, SharedRegistry>,
+) -> Result<ManifestDiagnostics, String> {
+    let context = {
+        let registry = registry.read().await;
+        let providers = registry.list_all_info();
+        DiagnosticContext {
+            known_providers: providers.iter().map(|p| p.id.clone()).collect(),
+            enabled_providers: providers
+                .iter()
+                .filter(|p| p.enabled)
+                .map(|p| p.id.clone())
+                .collect(),
+        }
+    };
+
+    diagnose_manifest_file(Path::new(&path), &context)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Rewrites a manifest with its keys in canonical order.
+#[tauri::command]
+pub async fn manifest_format(path: String) -> Result<ManifestFormatResult, String> {
+    format_manifest_file(Path::new(&path))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,53 @@
+use crate::commands::download::SharedDownloadManager;
+use crate::config::Settings;
+use crate::core::metrics;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, State};
+use tokio::sync::RwLock;
+
+pub type SharedSettings = Arc<RwLock<Settings>>;
+
+async fn render_metrics(settings: &SharedSettings, downloads: &SharedDownloadManager) -> String {
+    let cache_dir = settings.read().await.get_cache_dir();
+    let active_downloads = downloads.read().await.stats().await.downloading;
+    let snapshot = metrics::collect_snapshot(&cache_dir, active_downloads).await;
+    metrics::render_prometheus(&snapshot)
+}
+
+/// Render cache, download and install counters as a Prometheus text document
+#[tauri::command]
+pub async fn metrics_export(
+    settings: State<'_, SharedSettings>,
+    download_manager: State<'_, SharedDownloadManager>,
+) -> Result<String, String> {
+    Ok(render_metrics(settings.inner(), download_manager.inner()).await)
+}
+
+/// Start or stop the loopback `/metrics` listener to match the current settings
+pub async fn apply_metrics_listener(app: &AppHandle) {
+    let settings = app.state::<SharedSettings>().inner().clone();
+    let Some(downloads) = app
+        .try_state::<SharedDownloadManager>()
+        .map(|state| state.inner().clone())
+    else {
+        return;
+    };
+
+    let (enabled, port) = {
+        let s = settings.read().await;
+        (
+            s.general.metrics_listener_enabled,
+            s.general.metrics_listener_port,
+        )
+    };
+
+    let result = metrics::apply_listener(enabled, port, move || {
+        let settings = settings.clone();
+        let downloads = downloads.clone();
+        async move { render_metrics(&settings, &downloads).await }
+    })
+    .await;
+    if let Err(e) = result {
+        log::warn!("{}", e);
+    }
+}
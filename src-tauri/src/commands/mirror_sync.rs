@@ -0,0 +1,39 @@
+use crate::commands::package::SharedSettings;
+use crate::core::mirror_sync::{self, MirrorSyncResult, MirrorTool};
+use tauri::State;
+
+/// Write the configured mirrors and proxies into npm, pip, cargo, gem and
+/// composer configs. `preview` returns the diffs without writing; `force`
+/// overwrites mirrors the user set by hand.
+#[tauri::command]
+pub async fn mirror_sync_tools(
+    preview: bool,
+    force: Option<bool>,
+    tools: Option<Vec<MirrorTool>>,
+    settings: State<'_, SharedSettings>,
+) -> Result<Vec<MirrorSyncResult>, String> {
+    let settings = settings.read().await.clone();
+    let tools = tools.unwrap_or_else(|| MirrorTool::ALL.to_vec());
+    mirror_sync::sync_tools(
+        &settings.get_root_dir(),
+        &settings,
+        &tools,
+        preview,
+        force.unwrap_or(false),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Revert the values `mirror_sync_tools` wrote, keeping later user edits
+#[tauri::command]
+pub async fn mirror_unsync_tools(
+    tools: Option<Vec<MirrorTool>>,
+    settings: State<'_, SharedSettings>,
+) -> Result<Vec<MirrorSyncResult>, String> {
+    let root_dir = settings.read().await.get_root_dir();
+    let tools = tools.unwrap_or_else(|| MirrorTool::ALL.to_vec());
+    mirror_sync::unsync_tools(&root_dir, &tools)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -2,83 +2,126 @@
 pub mod batch;
 pub mod brew;
 pub mod cache;
+pub mod chocolatey;
 pub mod conda;
 pub mod config;
+pub mod cuda;
 pub mod custom_detection;
+pub mod custom_env_type;
+pub mod deep_link;
 pub mod diagnostic;
 pub mod download;
 pub mod environment;
 pub mod envvar;
 pub mod feedback;
 pub mod fs_utils;
+pub mod fvm;
 pub mod git;
 pub mod github;
 pub mod gitlab;
 pub mod health_check;
+pub mod jobs;
+pub mod juliaup;
 pub mod launch;
+pub mod local_api;
 pub mod log;
 pub mod macports;
 pub mod manifest;
+pub mod metrics;
+pub mod mirror_sync;
+pub mod notifications;
+pub mod onboarding;
 pub mod package;
+pub mod package_try;
 pub mod pipx;
+pub mod pixi;
 pub mod plugin;
 pub mod poetry;
 pub mod profiles;
+pub mod project_tasks;
+pub mod python_tools;
+pub mod release_notes;
+pub mod rig;
+pub mod scoop;
+pub mod sdkman;
 pub mod search;
 pub mod secrets;
 pub mod shim;
+pub mod stacks;
 pub mod terminal;
 pub mod toolbox;
+pub mod trash;
+pub mod update_schedule;
 pub mod updater;
 pub mod uv;
+pub mod volta;
 pub mod window_effect;
 pub mod window_effect_support;
 pub mod winget;
+pub mod workspace;
 pub mod wsl;
 pub mod xmake;
 
 pub use backup::{
-    backup_create, backup_delete, backup_list, backup_restore, backup_validate, db_get_info,
-    db_integrity_check,
+    backup_create, backup_delete, backup_full_create, backup_full_restore, backup_inspect,
+    backup_list, backup_restore, backup_validate, db_get_info, db_integrity_check,
 };
 pub use batch::{
     batch_install, batch_uninstall, batch_update, check_updates, clear_install_history,
     get_install_history, get_package_history, get_pinned_packages, package_pin, package_rollback,
-    package_unpin, resolve_dependencies,
+    package_unpin, resolve_dependencies, resolve_dependencies_explain,
 };
 pub use brew::{
-    brew_add_tap, brew_analytics_status, brew_analytics_toggle, brew_autoremove, brew_cleanup,
-    brew_doctor, brew_get_config, brew_list_pinned, brew_list_services, brew_list_taps, brew_pin,
-    brew_remove_tap, brew_service_restart, brew_service_start, brew_service_stop, brew_unpin,
+    brew_add_tap, brew_analytics_status, brew_analytics_toggle, brew_autoremove, brew_cask_info,
+    brew_cask_install, brew_cask_list, brew_cask_outdated, brew_cask_uninstall, brew_cask_upgrade,
+    brew_cleanup, brew_doctor, brew_get_config, brew_list_pinned, brew_list_services,
+    brew_list_taps, brew_pin, brew_remove_tap, brew_service_restart, brew_service_start,
+    brew_service_stop, brew_unpin,
 };
 pub use cache::{
     cache_clean, cache_clean_enhanced, cache_clean_preview, cache_force_clean,
-    cache_force_clean_external, cache_info, cache_migrate, cache_migration_validate, cache_repair,
+    cache_force_clean_external, cache_info, cache_migrate, cache_migration_validate,
+    cache_quarantine_list, cache_quarantine_purge, cache_quarantine_restore, cache_repair,
     cache_size_monitor, cache_verify, calculate_external_cache_size, clean_all_external_caches,
-    clean_external_cache, clear_cleanup_history, delete_cache_entries, delete_cache_entry,
-    discover_external_cache_candidates, discover_external_caches, discover_external_caches_fast,
-    get_cache_access_stats, get_cache_path_info, get_cache_settings, get_cleanup_history,
-    get_cleanup_summary, get_combined_cache_stats, get_external_cache_paths,
-    get_top_accessed_entries, list_cache_entries, probe_external_cache_provider,
-    reset_cache_access_stats, reset_cache_path, set_cache_path, set_cache_settings,
+    clean_external_cache, clean_external_cache_preview, cleanup_analytics, clear_cleanup_history,
+    delete_cache_entries, delete_cache_entry, discover_external_cache_candidates,
+    discover_external_caches, discover_external_caches_fast, get_cache_access_stats,
+    get_cache_path_info, get_cache_settings, get_cleanup_history, get_cleanup_summary,
+    get_combined_cache_stats, get_external_cache_paths, get_top_accessed_entries,
+    list_cache_entries, probe_external_cache_provider, reset_cache_access_stats, reset_cache_path,
+    set_cache_path, set_cache_settings,
+};
+pub use chocolatey::{
+    choco_pin, choco_pin_list, choco_source_add, choco_source_list, choco_source_remove,
 };
 pub use conda::{
     conda_channel_add, conda_channel_remove, conda_clean, conda_config_set, conda_config_show,
-    conda_env_clone, conda_env_create, conda_env_export, conda_env_import, conda_env_list,
-    conda_env_remove, conda_env_rename, conda_info,
+    conda_env_clone, conda_env_create, conda_env_diff, conda_env_disk_usage, conda_env_export,
+    conda_env_import, conda_env_list, conda_env_prune, conda_env_prune_preview, conda_env_remove,
+    conda_env_rename, conda_env_sync, conda_info, conda_lock_install,
 };
 pub use config::{
-    app_check_init, config_export, config_get, config_import, config_list, config_reset,
-    config_set, detect_system_proxy, get_battery_info, get_cognia_dir, get_components_info,
-    get_disk_info, get_network_interfaces, get_platform_info, test_proxy_connection,
+    app_check_init, config_export, config_get, config_import, config_import_preview, config_list,
+    config_reset, config_set, config_take_load_report, detect_system_proxy, get_battery_info,
+    get_cognia_dir, get_components_info, get_disk_info, get_network_interfaces, get_platform_info,
+    team_config_apply, team_config_export, team_config_preview, team_config_status,
+    test_proxy_connection,
 };
+pub use cuda::{cuda_detect_compatibility, cuda_list_installed};
 pub use custom_detection::{
-    create_shared_custom_detection_manager, custom_rule_add, custom_rule_delete,
-    custom_rule_detect, custom_rule_detect_all, custom_rule_export, custom_rule_extraction_types,
-    custom_rule_get, custom_rule_import, custom_rule_import_presets, custom_rule_list,
-    custom_rule_list_by_env, custom_rule_presets, custom_rule_test, custom_rule_toggle,
-    custom_rule_update, custom_rule_validate_regex, SharedCustomDetectionManager,
-};
+    create_shared_custom_detection_manager, custom_rule_add, custom_rule_approve_command,
+    custom_rule_delete, custom_rule_detect, custom_rule_detect_all, custom_rule_export,
+    custom_rule_extraction_types, custom_rule_get, custom_rule_import, custom_rule_import_presets,
+    custom_rule_list, custom_rule_list_by_env, custom_rule_presets, custom_rule_test,
+    custom_rule_toggle, custom_rule_update, custom_rule_validate_regex,
+    SharedCustomDetectionManager,
+};
+pub use custom_env_type::{
+    create_shared_custom_env_type_manager, custom_env_type_add, custom_env_type_approve_commands,
+    custom_env_type_delete, custom_env_type_get, custom_env_type_list, custom_env_type_update,
+    custom_env_type_validate, SharedCustomEnvTypeManager,
+};
+pub use deep_link::{deeplink_parse, deeplink_take_pending};
 pub use diagnostic::{
     diagnostic_capture_frontend_crash, diagnostic_check_last_crash, diagnostic_dismiss_crash,
     diagnostic_export_bundle, diagnostic_get_default_export_path, diagnostic_list_crash_reports,
@@ -89,45 +132,56 @@
     download_batch_remove, download_batch_resume, download_calculate_checksum, download_cancel,
     download_cancel_all, download_clear_finished, download_extract, download_get,
     download_get_max_concurrent, download_get_speed_limit, download_history_clear,
-    download_history_list, download_history_remove, download_history_search,
-    download_history_stats, download_list, download_open_file, download_pause, download_pause_all,
-    download_remove, download_resume, download_resume_all, download_retry, download_retry_failed,
-    download_reveal_file, download_set_max_concurrent, download_set_priority,
-    download_set_speed_limit, download_set_task_speed_limit, download_shutdown, download_stats,
-    download_verify_file, setup_download_manager, SharedDownloadManager,
+    download_history_export, download_history_import, download_history_list,
+    download_history_remove, download_history_search, download_history_stats, download_list,
+    download_open_file, download_pause, download_pause_all, download_remove, download_resume,
+    download_resume_all, download_retry, download_retry_failed, download_reveal_file,
+    download_set_max_concurrent, download_set_priority, download_set_speed_limit,
+    download_set_task_speed_limit, download_shutdown, download_stats, download_usage_reset,
+    download_usage_stats, download_validate_partials, download_verify_file, setup_download_manager,
+    SharedDownloadManager,
 };
 pub use environment::{
-    env_available_versions, env_check_updates, env_check_updates_all, env_cleanup_versions,
-    env_current_version, env_detect, env_detect_all, env_get, env_get_eol_info,
-    env_get_version_eol, env_install, env_install_cancel, env_installed_versions, env_list,
+    dotnet_list_runtimes, env_available_versions, env_check_updates, env_check_updates_all,
+    env_cleanup_versions, env_current_version, env_detect, env_detect_all, env_disk_usage,
+    env_explain_resolution, env_get, env_get_eol_info, env_get_version_eol, env_install,
+    env_install_batch, env_install_cancel, env_installed_versions, env_list,
     env_list_global_packages, env_list_providers, env_load_settings, env_migrate_packages,
-    env_resolve_alias, env_save_settings, env_uninstall, env_use_global, env_use_local,
-    env_verify_install, go_cache_info, go_clean_cache, go_env_info, go_mod_download, go_mod_tidy,
-    rustup_add_component, rustup_add_target, rustup_get_profile, rustup_list_components,
+    env_node_package_managers, env_reclaim_suggestions, env_refresh_versions, env_remove_broken,
+    env_resolve_alias, env_save_settings, env_smoke_test_approve, env_uninstall,
+    env_update_global_packages, env_upgrade_advice, env_use_global, env_use_local,
+    env_validate_installs, env_verify_install, go_cache_info, go_clean_cache, go_env_info,
+    go_mod_download, go_mod_tidy, go_work_info, go_work_sync, import_from_version_managers,
+    rustup_add_component, rustup_add_target, rustup_gc, rustup_get_profile, rustup_list_components,
     rustup_list_targets, rustup_override_list, rustup_override_set, rustup_override_unset,
     rustup_remove_component, rustup_remove_target, rustup_run, rustup_self_update,
-    rustup_set_profile, rustup_show, rustup_update_all, rustup_which,
+    rustup_set_profile, rustup_show, rustup_toolchain_sizes, rustup_update_all, rustup_which,
 };
 pub use envvar::{
-    envvar_add_path_entry, envvar_create_snapshot, envvar_deduplicate_path, envvar_delete_snapshot,
-    envvar_detect_conflicts, envvar_expand, envvar_export_env_file, envvar_get,
-    envvar_get_backup_protection, envvar_get_overview, envvar_get_path, envvar_get_persistent,
-    envvar_get_support_snapshot, envvar_import_env_file, envvar_list_all, envvar_list_persistent,
-    envvar_list_persistent_typed, envvar_list_shell_profiles, envvar_list_snapshots,
-    envvar_preview_snapshot_restore, envvar_read_shell_profile, envvar_remove_path_entry,
-    envvar_remove_persistent, envvar_remove_process, envvar_reorder_path, envvar_restore_snapshot,
-    envvar_set_persistent, envvar_set_process,
+    envvar_add_path_entry, envvar_apply_batch, envvar_create_snapshot, envvar_deduplicate_path,
+    envvar_delete_snapshot, envvar_detect_conflicts, envvar_expand, envvar_export_env_file,
+    envvar_export_merged_env_file, envvar_get, envvar_get_backup_protection, envvar_get_overview,
+    envvar_get_path, envvar_get_persistent, envvar_get_support_snapshot, envvar_import_env_file,
+    envvar_list_all, envvar_list_persistent, envvar_list_persistent_typed,
+    envvar_list_shell_profiles, envvar_list_snapshots, envvar_preview_snapshot_restore,
+    envvar_read_shell_profile, envvar_remove_path_entry, envvar_remove_persistent,
+    envvar_remove_process, envvar_reorder_path, envvar_resolve_env_files, envvar_restore_snapshot,
+    envvar_set_persistent, envvar_set_process, path_shadow_report,
 };
 pub use feedback::{
     feedback_count, feedback_delete, feedback_export, feedback_get, feedback_list, feedback_save,
 };
+pub use fvm::{
+    fvm_doctor, fvm_install, fvm_install_script_preview, fvm_list_installed, fvm_list_releases,
+    fvm_project_status, fvm_remove, fvm_self_install, fvm_use,
+};
 pub use git::{
     git_add_submodule, git_add_to_gitignore, git_add_worktree, git_apply_mailbox, git_apply_patch,
     git_archive, git_bisect_bad, git_bisect_good, git_bisect_log, git_bisect_reset,
     git_bisect_skip, git_bisect_start, git_branch_rename, git_branch_set_upstream,
     git_cancel_clone, git_check_ignore, git_checkout_branch, git_cherry_pick,
     git_cherry_pick_abort, git_cherry_pick_continue, git_clean, git_clean_dry_run, git_clone,
-    git_commit, git_create_branch, git_create_tag, git_deepen, git_delete_branch,
+    git_commit, git_commit_amend, git_create_branch, git_create_tag, git_deepen, git_delete_branch,
     git_delete_remote_branch, git_delete_tag, git_describe, git_discard_changes,
     git_extract_repo_name, git_fetch, git_format_patch, git_fsck, git_get_activity,
     git_get_ahead_behind, git_get_bisect_state, git_get_blame, git_get_branches,
@@ -144,12 +198,12 @@
     git_list_hooks, git_list_submodules, git_list_worktrees, git_merge, git_merge_abort,
     git_merge_continue, git_open_config_in_editor, git_probe_editor_capability,
     git_prune_worktrees, git_pull, git_push, git_push_tags, git_rebase, git_rebase_abort,
-    git_rebase_continue, git_rebase_skip, git_remote_add, git_remote_prune, git_remote_remove,
-    git_remote_rename, git_remote_set_url, git_remove_config, git_remove_local_config,
-    git_remove_submodule, git_remove_worktree, git_reset, git_resolve_file_mark,
-    git_resolve_file_ours, git_resolve_file_theirs, git_revert, git_revert_abort,
-    git_search_commits, git_set_config, git_set_config_if_unset, git_set_gitignore,
-    git_set_hook_content, git_set_local_config, git_sparse_checkout_add,
+    git_rebase_continue, git_rebase_execute, git_rebase_plan, git_rebase_skip, git_rebase_status,
+    git_remote_add, git_remote_prune, git_remote_remove, git_remote_rename, git_remote_set_url,
+    git_remove_config, git_remove_local_config, git_remove_submodule, git_remove_worktree,
+    git_reset, git_resolve_file_mark, git_resolve_file_ours, git_resolve_file_theirs, git_revert,
+    git_revert_abort, git_search_commits, git_set_config, git_set_config_if_unset,
+    git_set_gitignore, git_set_hook_content, git_set_local_config, git_sparse_checkout_add,
     git_sparse_checkout_disable, git_sparse_checkout_init, git_sparse_checkout_list,
     git_sparse_checkout_set, git_squash, git_stage_all, git_stage_files,
     git_start_interactive_rebase, git_stash_apply, git_stash_branch, git_stash_drop, git_stash_pop,
@@ -161,8 +215,8 @@
     github_clear_token, github_download_asset, github_download_source,
     github_download_workflow_artifact, github_get_release_assets, github_get_repo_info,
     github_get_token, github_list_branches, github_list_releases, github_list_tags,
-    github_list_workflow_artifacts, github_parse_url, github_set_token, github_validate_repo,
-    github_validate_token,
+    github_list_workflow_artifacts, github_parse_url, github_pick_asset_explain,
+    github_rate_limit_status, github_set_token, github_validate_repo, github_validate_token,
 };
 pub use gitlab::{
     gitlab_clear_token, gitlab_download_asset, gitlab_download_job_artifacts,
@@ -170,122 +224,180 @@
     gitlab_get_project_info, gitlab_get_release_assets, gitlab_get_token, gitlab_list_branches,
     gitlab_list_package_files, gitlab_list_packages, gitlab_list_pipeline_jobs,
     gitlab_list_pipelines, gitlab_list_releases, gitlab_list_tags, gitlab_parse_url,
-    gitlab_search_projects, gitlab_set_instance_url, gitlab_set_token, gitlab_validate_project,
-    gitlab_validate_token,
+    gitlab_rate_limit_status, gitlab_search_projects, gitlab_set_instance_url, gitlab_set_token,
+    gitlab_validate_project, gitlab_validate_token,
 };
 pub use health_check::{
     health_check_all, health_check_environment, health_check_fix, health_check_package_manager,
     health_check_package_managers,
 };
+pub use jobs::{jobs_cancel, jobs_get, jobs_list};
+pub use juliaup::juliaup_self_install;
 pub use launch::{
-    env_activate, env_get_info, exec_shell_with_env, launch_with_env, launch_with_streaming,
-    which_program,
+    env_activate, env_get_info, exec_shell_with_env, launch_get_stats, launch_list_running,
+    launch_stop, launch_with_env, launch_with_streaming, which_program, which_program_detailed,
 };
+pub use local_api::api_server_status;
 pub use log::{
     log_cleanup, log_cleanup_preview, log_clear, log_delete_batch, log_delete_file, log_export,
-    log_get_dir, log_get_total_size, log_list_files, log_query,
+    log_follow_start, log_follow_stop, log_get_dir, log_get_total_size, log_list_files, log_query,
+    log_stats,
 };
 pub use macports::{
     macports_clean_all, macports_list_select_groups, macports_list_variants, macports_port_clean,
     macports_port_contents, macports_port_dependents, macports_reclaim, macports_select_options,
     macports_select_set, macports_selfupdate,
 };
-pub use manifest::{manifest_init, manifest_read};
+pub use manifest::{manifest_diagnostics, manifest_format, manifest_init, manifest_read};
+pub use metrics::metrics_export;
+pub use mirror_sync::{mirror_sync_tools, mirror_unsync_tools};
+pub use notifications::{
+    notifications_clear, notifications_get_unread_count, notifications_list,
+    notifications_mark_read,
+};
+pub use onboarding::{
+    onboarding_complete_step, onboarding_reset, onboarding_skip_step, onboarding_status,
+};
 pub use package::{
     package_check_installed, package_info, package_install, package_list, package_search,
-    package_uninstall, package_versions, pre_install_validate, provider_check, provider_disable,
-    provider_enable, provider_list, provider_status_all, provider_system_list,
-    resolve_dependency_conflict,
+    package_uninstall, package_versions, pre_install_validate, provider_cache_warm, provider_check,
+    provider_disable, provider_enable, provider_list, provider_selftest, provider_status_all,
+    provider_system_list, resolve_dependency_conflict,
+};
+pub use package_try::{
+    package_try, package_try_list, package_try_promote, package_try_remove, PackageTryInfo,
 };
 pub use pipx::{
     pipx_ensurepath, pipx_inject, pipx_list_json, pipx_reinstall_all, pipx_run, pipx_upgrade,
     pipx_upgrade_all,
 };
+pub use pixi::{
+    pixi_add, pixi_env_info, pixi_global_install, pixi_global_list, pixi_global_remove,
+    pixi_install, pixi_list, pixi_project_init, pixi_remove, pixi_run,
+};
 pub use plugin::{
-    plugin_call_tool, plugin_check_all_updates, plugin_disable, plugin_dispatch_event,
-    plugin_enable, plugin_export_data, plugin_get_all_health, plugin_get_data_dir,
-    plugin_get_health, plugin_get_info, plugin_get_locales, plugin_get_permissions,
-    plugin_get_settings_schema, plugin_get_settings_values, plugin_get_tools, plugin_get_ui_asset,
-    plugin_get_ui_entry, plugin_grant_permission, plugin_import_local, plugin_install,
-    plugin_install_marketplace, plugin_install_marketplace_with_result, plugin_list,
-    plugin_list_all_tools, plugin_open_scaffold_folder, plugin_open_scaffold_in_vscode,
-    plugin_reload, plugin_reset_health, plugin_revoke_permission, plugin_scaffold,
-    plugin_set_setting, plugin_uninstall, plugin_update_all, plugin_update_with_result,
-    plugin_validate, toolbox_cancel_tool, SharedPluginManager,
+    plugin_call_tool, plugin_check_all_updates, plugin_check_updates, plugin_disable,
+    plugin_dispatch_event, plugin_enable, plugin_export_data, plugin_get_all_health,
+    plugin_get_data_dir, plugin_get_health, plugin_get_info, plugin_get_locales,
+    plugin_get_permissions, plugin_get_settings_resolved, plugin_get_settings_schema,
+    plugin_get_settings_values, plugin_get_tools, plugin_get_ui_asset, plugin_get_ui_entry,
+    plugin_grant_permission, plugin_import_local, plugin_install, plugin_install_marketplace,
+    plugin_install_marketplace_with_result, plugin_list, plugin_list_all_tools,
+    plugin_marketplace_install, plugin_marketplace_list, plugin_marketplace_refresh,
+    plugin_open_scaffold_folder, plugin_open_scaffold_in_vscode, plugin_reload,
+    plugin_reset_health, plugin_revoke_permission, plugin_scaffold, plugin_set_setting,
+    plugin_storage_clear, plugin_storage_inspect, plugin_uninstall, plugin_update_all,
+    plugin_update_with_result, plugin_validate, toolbox_cancel_tool, SharedPluginManager,
 };
 pub use poetry::{
-    poetry_check, poetry_env_list, poetry_env_remove, poetry_env_use, poetry_export, poetry_lock,
-    poetry_run, poetry_update, poetry_version,
+    poetry_add, poetry_check, poetry_env_list, poetry_env_remove, poetry_env_use, poetry_export,
+    poetry_groups_list, poetry_lock, poetry_remove, poetry_run, poetry_show, poetry_update,
+    poetry_version,
 };
 pub use profiles::{
     profile_apply, profile_apply_wsl_snapshot, profile_capture_wsl_snapshot, profile_create,
     profile_create_from_current, profile_delete, profile_export, profile_get, profile_import,
-    profile_list, profile_update,
+    profile_import_preview, profile_list, profile_update,
+};
+pub use project_tasks::{project_task_run, project_tasks_list};
+pub use python_tools::{
+    python_tool_install, python_tool_list, python_tool_migrate, python_tool_uninstall,
+    python_tool_upgrade,
+};
+pub use release_notes::release_notes_get;
+pub use rig::rig_self_install;
+pub use scoop::{
+    scoop_bucket_add, scoop_bucket_list, scoop_bucket_remove, scoop_cleanup, scoop_hold,
+    scoop_unhold,
+};
+pub use sdkman::{sdkman_install_script_preview, sdkman_self_install};
+pub use search::{
+    advanced_search, compare_packages, global_search, global_search_cancel, search_suggestions,
 };
-pub use search::{advanced_search, compare_packages, search_suggestions};
 pub use secrets::{
     provider_secret_clear_internal, provider_secret_save_internal, provider_secret_status_internal,
-    resolve_provider_secret, secret_vault_lock, secret_vault_reset, secret_vault_setup,
-    secret_vault_status, secret_vault_unlock, ProviderSecretStatus, SecretVaultStatus,
+    resolve_provider_secret, secret_delete, secret_get, secret_list, secret_set,
+    secret_store_status, secret_vault_lock, secret_vault_reset, secret_vault_setup,
+    secret_vault_status, secret_vault_unlock, ProviderSecretStatus, SecretStoreStatus,
+    SecretVaultStatus,
 };
 pub use shim::{
-    path_check, path_get_add_command, path_remove, path_setup, path_status, shim_create, shim_list,
-    shim_regenerate_all, shim_remove, shim_update,
+    path_check, path_get_add_command, path_get_init_script, path_remove, path_setup, path_status,
+    shim_create, shim_generate_completions, shim_list, shim_regenerate_all, shim_remove,
+    shim_update,
+};
+pub use stacks::{
+    stack_apply, stack_create_from_current, stack_export, stack_import, stack_preview,
 };
 pub use terminal::{
-    terminal_append_to_config, terminal_append_to_config_verified, terminal_backup_config,
-    terminal_backup_config_verified, terminal_check_shell_health, terminal_clean_framework_cache,
-    terminal_create_custom_template, terminal_create_profile,
+    shell_profile_analyze, shell_profile_apply_fix, terminal_append_to_config,
+    terminal_append_to_config_verified, terminal_backup_config, terminal_backup_config_verified,
+    terminal_check_shell_health, terminal_clean_framework_cache, terminal_config_remove_entry,
+    terminal_config_upsert_entry, terminal_create_custom_template, terminal_create_profile,
     terminal_create_profile_from_template, terminal_delete_custom_template,
     terminal_delete_profile, terminal_detect_framework, terminal_detect_shells,
     terminal_duplicate_profile, terminal_export_profiles, terminal_get_config_editor_metadata,
     terminal_get_config_entries, terminal_get_default_profile, terminal_get_framework_cache_stats,
     terminal_get_profile, terminal_get_proxy_env_vars, terminal_get_shell_env_vars,
     terminal_get_shell_info, terminal_get_single_framework_cache_info, terminal_import_profiles,
-    terminal_launch_profile, terminal_launch_profile_detailed, terminal_list_plugins,
-    terminal_list_profiles, terminal_list_templates, terminal_measure_startup,
-    terminal_parse_config_content, terminal_ps_find_module, terminal_ps_get_execution_policy,
-    terminal_ps_get_module_detail, terminal_ps_install_module, terminal_ps_list_all_modules,
-    terminal_ps_list_installed_scripts, terminal_ps_list_profiles, terminal_ps_read_profile,
-    terminal_ps_set_execution_policy, terminal_ps_uninstall_module, terminal_ps_update_module,
-    terminal_ps_write_profile, terminal_read_config, terminal_restore_config_snapshot,
-    terminal_save_profile_as_template, terminal_set_default_profile, terminal_update_profile,
-    terminal_validate_config_content, terminal_write_config, terminal_write_config_verified,
-    SharedTerminalProfileManager,
+    terminal_launch_history, terminal_launch_history_pin, terminal_launch_profile,
+    terminal_launch_profile_detailed, terminal_list_plugins, terminal_list_profiles,
+    terminal_list_templates, terminal_measure_startup, terminal_parse_config_content,
+    terminal_ps_find_module, terminal_ps_get_execution_policy, terminal_ps_get_module_detail,
+    terminal_ps_install_module, terminal_ps_list_all_modules, terminal_ps_list_installed_scripts,
+    terminal_ps_list_profiles, terminal_ps_read_profile, terminal_ps_set_execution_policy,
+    terminal_ps_uninstall_module, terminal_ps_update_all_modules, terminal_ps_update_module,
+    terminal_ps_write_profile, terminal_read_config, terminal_relaunch,
+    terminal_restore_config_snapshot, terminal_save_profile_as_template, terminal_session_close,
+    terminal_session_list, terminal_session_open, terminal_session_resize, terminal_session_write,
+    terminal_set_default_profile, terminal_update_profile, terminal_validate_config_content,
+    terminal_write_config, terminal_write_config_verified, SharedTerminalProfileManager,
 };
 pub use toolbox::{
     toolbox_hash_file, toolbox_read_file_for_tool, toolbox_resolve_path, toolbox_write_tool_output,
 };
-pub use updater::{self_check_update, self_update};
+pub use trash::{trash_list, trash_purge, trash_restore};
+pub use update_schedule::{
+    schedule_update_check_disable, schedule_update_check_enable, schedule_update_check_last_report,
+    schedule_update_check_status,
+};
+pub use updater::{self_check_update, self_list_available_versions, self_update};
 pub use uv::{
-    uv_add, uv_cache_clean, uv_cache_dir, uv_init, uv_lock, uv_pip_compile, uv_python_install,
-    uv_python_list, uv_python_pin, uv_python_uninstall, uv_remove, uv_run, uv_self_update, uv_sync,
-    uv_tool_install, uv_tool_list, uv_tool_run, uv_tool_uninstall, uv_tree, uv_venv_create,
-    uv_version,
+    uv_add, uv_cache_clean, uv_cache_dir, uv_init, uv_lock, uv_pip_compile, uv_python_find,
+    uv_python_install, uv_python_list, uv_python_pin, uv_python_uninstall, uv_remove, uv_run,
+    uv_run_script, uv_script_deps, uv_self_update, uv_sync, uv_tool_install, uv_tool_list,
+    uv_tool_run, uv_tool_uninstall, uv_tree, uv_venv_create, uv_version, uv_workspace_list,
 };
+pub use volta::{volta_install, volta_list, volta_pin, volta_status, volta_which};
 pub use window_effect::{window_effect_apply, window_effect_clear, window_effect_get_supported};
 pub use winget::{
     winget_download, winget_export, winget_get_info, winget_import, winget_install_advanced,
-    winget_pin_add, winget_pin_list, winget_pin_remove, winget_pin_reset, winget_repair,
-    winget_source_add, winget_source_list, winget_source_remove, winget_source_reset,
+    winget_install_environment, winget_pin_add, winget_pin_list, winget_pin_remove,
+    winget_pin_reset, winget_repair, winget_source_add, winget_source_list, winget_source_remove,
+    winget_source_reset,
+};
+pub use workspace::{
+    workspace_apply_envs, workspace_create, workspace_delete, workspace_list, workspace_scan,
+    workspace_status, SharedWorkspaceManager,
 };
 pub use wsl::{
-    wsl_add_port_forward, wsl_backup_distro, wsl_batch_launch, wsl_batch_terminate,
-    wsl_change_default_user, wsl_clone_distro, wsl_convert_path, wsl_debug_detection,
-    wsl_delete_backup, wsl_detect_distro_env, wsl_disk_usage, wsl_distro_health_check, wsl_exec,
-    wsl_export, wsl_export_windows_env, wsl_get_capabilities, wsl_get_config,
-    wsl_get_distro_config, wsl_get_ip, wsl_get_runtime_snapshot, wsl_get_version_info,
-    wsl_get_wslenv, wsl_import, wsl_import_in_place, wsl_install_with_location,
-    wsl_install_wsl_only, wsl_is_available, wsl_launch, wsl_list_backups, wsl_list_distros,
-    wsl_list_online, wsl_list_port_forwards, wsl_list_running, wsl_mount, wsl_move_distro,
-    wsl_open_in_explorer, wsl_open_in_terminal, wsl_read_distro_env, wsl_remove_port_forward,
-    wsl_resize_distro, wsl_restore_backup, wsl_set_config, wsl_set_default,
-    wsl_set_default_version, wsl_set_distro_config, wsl_set_networking_mode, wsl_set_sparse,
-    wsl_set_version, wsl_set_wslenv, wsl_shutdown, wsl_status, wsl_terminate, wsl_total_disk_usage,
-    wsl_unmount, wsl_update,
+    wsl_add_port_forward, wsl_backup_distro, wsl_batch_exec, wsl_batch_export, wsl_batch_launch,
+    wsl_batch_terminate, wsl_batch_update, wsl_change_default_user, wsl_clone_distro,
+    wsl_config_schema, wsl_config_set_key, wsl_config_validate, wsl_convert_path,
+    wsl_debug_detection, wsl_delete_backup, wsl_detect_distro_env, wsl_disk_usage,
+    wsl_distro_health_check, wsl_exec, wsl_export, wsl_export_windows_env, wsl_get_capabilities,
+    wsl_get_config, wsl_get_distro_config, wsl_get_ip, wsl_get_runtime_snapshot,
+    wsl_get_version_info, wsl_get_wslenv, wsl_import, wsl_import_in_place,
+    wsl_install_with_location, wsl_install_wsl_only, wsl_is_available, wsl_launch,
+    wsl_list_backups, wsl_list_distros, wsl_list_online, wsl_list_port_forwards, wsl_list_running,
+    wsl_mount, wsl_move_distro, wsl_open_in_explorer, wsl_open_in_terminal, wsl_read_distro_env,
+    wsl_remove_port_forward, wsl_resize_distro, wsl_restore_backup, wsl_set_config,
+    wsl_set_default, wsl_set_default_version, wsl_set_distro_config, wsl_set_networking_mode,
+    wsl_set_sparse, wsl_set_version, wsl_set_wslenv, wsl_shutdown, wsl_status, wsl_terminate,
+    wsl_total_disk_usage, wsl_unmount, wsl_update,
 };
 pub use xmake::{
-    xmake_add_repo, xmake_clean_cache, xmake_download_source, xmake_env_bind, xmake_env_list,
-    xmake_env_show, xmake_export_package, xmake_import_package, xmake_list_repos,
-    xmake_remove_repo, xmake_update_repos,
+    xmake_add_repo, xmake_cache_stats, xmake_clean_cache, xmake_download_source, xmake_env_bind,
+    xmake_env_list, xmake_env_show, xmake_export_package, xmake_import_package, xmake_list_repos,
+    xmake_project_deps, xmake_project_deps_export, xmake_remove_repo, xmake_update_repos,
 };
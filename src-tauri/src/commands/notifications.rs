@@ -0,0 +1,173 @@
+use crate::commands::config::SharedSettings;
+use crate::core::notifications::{
+    Notification, NotificationCategory, NotificationDelivery, NotificationFilter,
+    NotificationRecord, NotificationSeverity, SharedNotificationCenter,
+};
+use crate::tray::{self, TrayNotificationEvent};
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
+
+/// Tray notification event that gates toasts of each category
+fn tray_event_for(category: NotificationCategory) -> TrayNotificationEvent {
+    match category {
+        NotificationCategory::Download => TrayNotificationEvent::Downloads,
+        NotificationCategory::Update | NotificationCategory::Eol => TrayNotificationEvent::Updates,
+        NotificationCategory::InstallFailure => TrayNotificationEvent::Errors,
+        NotificationCategory::System => TrayNotificationEvent::System,
+    }
+}
+
+/// Record a notification and deliver it according to its category
+/// preference. All backend notifications go through here.
+pub async fn notify<R: Runtime>(app: &AppHandle<R>, notification: Notification) {
+    let event = tray_event_for(notification.category);
+    if let Err(e) = deliver(app, notification, Some(event)).await {
+        log::warn!("Failed to show notification: {}", e);
+    }
+}
+
+/// Record the notification, then show a desktop toast when the category is
+/// set to `toast` and the tray notification toggles allow it. Only a failed
+/// toast is reported as an error; recording failures are logged.
+async fn deliver<R: Runtime>(
+    app: &AppHandle<R>,
+    notification: Notification,
+    event: Option<TrayNotificationEvent>,
+) -> Result<(), String> {
+    use tauri_plugin_notification::NotificationExt;
+
+    let delivery = match app.try_state::<SharedSettings>() {
+        Some(settings) => settings
+            .read()
+            .await
+            .notifications
+            .delivery_for(notification.category),
+        None => NotificationDelivery::default(),
+    };
+    let important = notification.severity.is_important();
+    let (title, body) = (notification.title.clone(), notification.body.clone());
+
+    if let Some(center) = app.try_state::<SharedNotificationCenter>() {
+        match center.record(notification, delivery).await {
+            Ok(record) => {
+                let _ = app.emit("notification-added", &record);
+            }
+            Err(e) => log::warn!("Failed to record notification: {}", e),
+        }
+        refresh_unread_count(app, &center).await;
+    }
+
+    if delivery != NotificationDelivery::Toast || !tray::toast_allowed(app, event, important).await
+    {
+        return Ok(());
+    }
+    app.notification()
+        .builder()
+        .title(&title)
+        .body(&body)
+        .show()
+        .map_err(|e| e.to_string())
+}
+
+/// Push the unread count to the tray and the frontend
+pub async fn refresh_unread_count<R: Runtime>(
+    app: &AppHandle<R>,
+    center: &SharedNotificationCenter,
+) {
+    let count = match center.unread_count().await {
+        Ok(count) => count,
+        Err(e) => {
+            log::warn!("Failed to count unread notifications: {}", e);
+            return;
+        }
+    };
+    let _ = app.emit("notifications-unread-changed", count);
+    if let Err(e) = tray::set_unread_notifications(app, count).await {
+        log::debug!("Failed to update tray unread count: {}", e);
+    }
+}
+
+/// Notification sent by the frontend through `tray_send_notification`
+pub(crate) async fn notify_from_frontend<R: Runtime>(
+    app: &AppHandle<R>,
+    title: String,
+    body: String,
+    important: bool,
+    event: Option<TrayNotificationEvent>,
+) -> Result<(), String> {
+    let (category, severity) = match event {
+        Some(TrayNotificationEvent::Updates) => {
+            (NotificationCategory::Update, NotificationSeverity::Info)
+        }
+        Some(TrayNotificationEvent::Downloads) => {
+            (NotificationCategory::Download, NotificationSeverity::Info)
+        }
+        Some(TrayNotificationEvent::Errors) => {
+            (NotificationCategory::System, NotificationSeverity::Error)
+        }
+        Some(TrayNotificationEvent::System) | None => {
+            (NotificationCategory::System, NotificationSeverity::Info)
+        }
+    };
+    let severity = if important {
+        severity.max(NotificationSeverity::Warning)
+    } else {
+        severity
+    };
+    deliver(
+        app,
+        Notification::new(category, severity, title, body),
+        event,
+    )
+    .await
+}
+
+/// Notification history, newest first
+#[tauri::command]
+pub async fn notifications_list(
+    filter: Option<NotificationFilter>,
+    center: State<'_, SharedNotificationCenter>,
+) -> Result<Vec<NotificationRecord>, String> {
+    center
+        .list(&filter.unwrap_or_default())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Mark notifications as read; all of them when `ids` is omitted.
+/// Returns the number of notifications that changed.
+#[tauri::command]
+pub async fn notifications_mark_read(
+    ids: Option<Vec<String>>,
+    app: AppHandle,
+    center: State<'_, SharedNotificationCenter>,
+) -> Result<usize, String> {
+    let changed = center
+        .mark_read(ids.as_deref())
+        .await
+        .map_err(|e| e.to_string())?;
+    refresh_unread_count(&app, &center).await;
+    Ok(changed)
+}
+
+/// Delete notifications; all of them when `ids` is omitted.
+/// Returns the number of notifications removed.
+#[tauri::command]
+pub async fn notifications_clear(
+    ids: Option<Vec<String>>,
+    app: AppHandle,
+    center: State<'_, SharedNotificationCenter>,
+) -> Result<usize, String> {
+    let removed = center
+        .clear(ids.as_deref())
+        .await
+        .map_err(|e| e.to_string())?;
+    refresh_unread_count(&app, &center).await;
+    Ok(removed)
+}
+
+#[tauri::command]
+pub async fn notifications_get_unread_count(
+    center: State<'_, SharedNotificationCenter>,
+) -> Result<usize, String> {
+    center.unread_count().await.map_err(|e| e.to_string())
+}
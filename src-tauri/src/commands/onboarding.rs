@@ -0,0 +1,165 @@
+use crate::commands::config::{ensure_data_dirs, replace_settings, SharedSettings};
+use crate::commands::environment::detect_system_environments;
+use crate::config::OnboardingSettings;
+use crate::core::onboarding::{
+    self, FeedbackPayload, MirrorsPayload, OnboardingStatus, OnboardingStep, OnboardingStepStatus,
+    PathSetupPayload, RootDirPayload,
+};
+use crate::core::{InitShell, PathManager};
+use crate::platform::fs;
+use crate::SharedRegistry;
+use tauri::{AppHandle, State};
+
+/// Apply `update` to the onboarding state and save the settings
+async fn save_onboarding(
+    settings: &SharedSettings,
+    update: impl FnOnce(&mut OnboardingSettings),
+) -> Result<(), String> {
+    let mut s = settings.write().await;
+    update(&mut s.onboarding);
+    s.save().await.map_err(|e| e.to_string())
+}
+
+fn parse_shells(ids: &[String]) -> Result<Vec<InitShell>, String> {
+    if ids.is_empty() {
+        return Ok(vec![InitShell::detect()]);
+    }
+    ids.iter()
+        .map(|id| InitShell::from_id(id).ok_or_else(|| format!("Unknown shell: {}", id)))
+        .collect()
+}
+
+/// Onboarding progress, with the step to resume at
+#[tauri::command]
+pub async fn onboarding_status(
+    settings: State<'_, SharedSettings>,
+) -> Result<OnboardingStatus, String> {
+    Ok(onboarding::status(&settings.read().await.onboarding))
+}
+
+/// Complete an onboarding step, doing its work first so the recorded
+/// status never runs ahead of it. Steps run in order; completing a step
+/// again repeats its work, which changes nothing the second time.
+#[tauri::command]
+pub async fn onboarding_complete_step(
+    step: OnboardingStep,
+    payload: Option<serde_json::Value>,
+    app: AppHandle,
+    settings: State<'_, SharedSettings>,
+    registry: State<'_, SharedRegistry>,
+) -> Result<OnboardingStatus, String> {
+    onboarding::check_transition(&settings.read().await.onboarding, step, false)
+        .map_err(|e| e.to_string())?;
+    let completed = OnboardingStepStatus::Completed;
+
+    match step {
+        OnboardingStep::RootDir => {
+            let payload: RootDirPayload =
+                onboarding::parse_payload(step, payload).map_err(|e| e.to_string())?;
+            let mut next = settings.read().await.clone();
+            if let Some(root) = payload.root_dir.as_deref().map(str::trim) {
+                if !root.is_empty() {
+                    next.set_value("paths.root", root)
+                        .map_err(|e| e.to_string())?;
+                }
+            }
+            let detail = next.get_root_dir().display().to_string();
+            onboarding::mark(&mut next.onboarding, step, completed, Some(detail));
+            replace_settings(&app, settings.inner(), registry.inner(), next).await?;
+            ensure_data_dirs(settings.inner()).await;
+        }
+        OnboardingStep::Mirrors => {
+            let payload: MirrorsPayload =
+                onboarding::parse_payload(step, payload).map_err(|e| e.to_string())?;
+            let mut keys: Vec<&String> = payload.values.keys().collect();
+            keys.sort();
+            let mut next = settings.read().await.clone();
+            for key in &keys {
+                if !onboarding::is_mirrors_step_key(key) {
+                    return Err(format!("'{}' cannot be set by the mirrors step", key));
+                }
+                next.set_value(key, &payload.values[*key])
+                    .map_err(|e| e.to_string())?;
+            }
+            let detail = format!("{} settings applied", keys.len());
+            onboarding::mark(&mut next.onboarding, step, completed, Some(detail));
+            replace_settings(&app, settings.inner(), registry.inner(), next).await?;
+        }
+        OnboardingStep::PathSetup => {
+            let payload: PathSetupPayload =
+                onboarding::parse_payload(step, payload).map_err(|e| e.to_string())?;
+            let shells = parse_shells(&payload.shells)?;
+            let shim_dir = settings.read().await.get_bin_dir();
+            fs::create_dir_all(&shim_dir)
+                .await
+                .map_err(|e| e.to_string())?;
+            let path_manager = PathManager::new(shim_dir);
+            for shell in &shells {
+                path_manager
+                    .add_to_path_for(*shell)
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+            let ids: Vec<String> = shells.iter().map(|s| s.id().to_string()).collect();
+            save_onboarding(settings.inner(), |state| {
+                for id in &ids {
+                    if !state.configured_shells.contains(id) {
+                        state.configured_shells.push(id.clone());
+                    }
+                }
+                onboarding::mark(state, step, completed, Some(ids.join(", ")));
+            })
+            .await?;
+        }
+        OnboardingStep::EnvDetect => {
+            let found = detect_system_environments(false, settings.inner()).await;
+            let detail = format!("{} environments detected", found.len());
+            save_onboarding(settings.inner(), |state| {
+                onboarding::mark(state, step, completed, Some(detail));
+            })
+            .await?;
+        }
+        OnboardingStep::Feedback => {
+            let payload: FeedbackPayload =
+                onboarding::parse_payload(step, payload).map_err(|e| e.to_string())?;
+            save_onboarding(settings.inner(), |state| {
+                state.feedback_opt_in = payload.opt_in;
+                onboarding::mark(state, step, completed, None);
+            })
+            .await?;
+        }
+    }
+
+    Ok(onboarding::status(&settings.read().await.onboarding))
+}
+
+/// Skip an optional onboarding step. Skipping the feedback step opts out;
+/// an already completed step stays completed.
+#[tauri::command]
+pub async fn onboarding_skip_step(
+    step: OnboardingStep,
+    settings: State<'_, SharedSettings>,
+) -> Result<OnboardingStatus, String> {
+    let mut s = settings.write().await;
+    onboarding::check_transition(&s.onboarding, step, true).map_err(|e| e.to_string())?;
+    if s.onboarding.record(step).status != OnboardingStepStatus::Completed {
+        if step == OnboardingStep::Feedback {
+            s.onboarding.feedback_opt_in = false;
+        }
+        onboarding::mark(&mut s.onboarding, step, OnboardingStepStatus::Skipped, None);
+        s.save().await.map_err(|e| e.to_string())?;
+    }
+    Ok(onboarding::status(&s.onboarding))
+}
+
+/// Start onboarding over. What completed steps changed (PATH, settings)
+/// is left in place.
+#[tauri::command]
+pub async fn onboarding_reset(
+    settings: State<'_, SharedSettings>,
+) -> Result<OnboardingStatus, String> {
+    let mut s = settings.write().await;
+    s.onboarding = OnboardingSettings::default();
+    s.save().await.map_err(|e| e.to_string())?;
+    Ok(onboarding::status(&s.onboarding))
+}
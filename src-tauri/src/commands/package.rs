@@ -1,18 +1,21 @@
-use crate::cache::MetadataCache;
+use crate::cache::{CachedMetadata, MetadataCache};
 use crate::commands::batch::ConflictInfo;
 use crate::config::Settings;
 use crate::core::{
     Orchestrator, PackagePreflightSummary, PackageValidationResult, ValidationContext,
     ValidationStatus, ValidatorChain,
 };
+use crate::error::{CogniaError, CogniaResult};
 use crate::platform::env::{current_platform, Platform};
 use crate::provider::{
+    require_capability,
+    selftest::{run_selftest, ProviderSelftestReport},
     support::{
         classify_provider_scope, provider_health_probe_timeout, update_support_reason,
         ProviderAvailabilityProbe, SUPPORT_STATUS_SUPPORTED, SUPPORT_STATUS_UNSUPPORTED,
     },
     Capability, InstalledFilter, InstalledPackage, PackageInfo, PackageSummary, Provider,
-    ProviderRegistry, SearchOptions,
+    ProviderHealthMetric, ProviderRegistry, SearchOptions, SystemPackageProvider,
 };
 use futures::future::join_all;
 use std::collections::{HashMap, HashSet};
@@ -75,7 +78,7 @@ pub async fn refresh_provider_registry(
     settings: &SharedSettings,
     registry: &SharedRegistry,
 ) -> Result<(), String> {
-    let rebuilt_registry = {
+    let mut rebuilt_registry = {
         let settings_guard = settings.read().await;
         ProviderRegistry::with_settings(&settings_guard)
             .await
@@ -83,6 +86,8 @@ pub async fn refresh_provider_registry(
     };
 
     let mut registry_guard = registry.write().await;
+    // User-defined environment types are not part of the settings-driven set
+    rebuilt_registry.copy_custom_environment_providers(&registry_guard);
     *registry_guard = rebuilt_registry;
     Ok(())
 }
@@ -114,81 +119,265 @@ async fn update_provider_management_state<F>(
     Ok(())
 }
 
-#[tauri::command]
-pub async fn package_search(
-    query: String,
-    provider: Option<String>,
-    force: Option<bool>,
-    registry: State<'_, SharedRegistry>,
-    settings: State<'_, SharedSettings>,
-) -> Result<Vec<PackageSummary>, String> {
-    let cache_key = format!(
-        "pkg:search:{}:{}",
-        provider.as_deref().unwrap_or("all"),
-        &query
-    );
+/// Search/info response with cache freshness metadata
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedResponse<T> {
+    pub data: T,
+    /// Data is past its TTL (served because the provider was unreachable or offline mode is on)
+    pub stale: bool,
+    pub from_cache: bool,
+    /// RFC 3339 time the cached entry was written
+    pub cached_at: Option<String>,
+    pub offline: bool,
+    /// Error from the failed provider request when stale data was returned instead
+    pub error: Option<String>,
+}
 
-    // Try cache first (unless forced)
-    if !force.unwrap_or(false) {
-        if let Ok(mut cache) = open_metadata_cache(settings.inner(), SEARCH_CACHE_TTL).await {
-            if let Ok(Some(cached)) = cache.get::<Vec<PackageSummary>>(&cache_key).await {
-                if !cached.is_stale {
-                    return Ok(cached.data);
-                }
-            }
+impl<T> CachedResponse<T> {
+    fn fresh(data: T) -> Self {
+        Self {
+            data,
+            stale: false,
+            from_cache: false,
+            cached_at: None,
+            offline: false,
+            error: None,
         }
     }
 
-    // Perform actual search
-    let results = {
-        let reg = registry.read().await;
+    fn from_cached(cached: CachedMetadata<T>, stale: bool) -> Self {
+        Self {
+            data: cached.data,
+            stale,
+            from_cache: true,
+            cached_at: Some(cached.cached_at.to_rfc3339()),
+            offline: false,
+            error: None,
+        }
+    }
+}
 
-        if let Some(ref provider_id) = provider {
-            if let Some(p) = reg.get(provider_id) {
-                p.search(&query, SearchOptions::default())
-                    .await
-                    .map_err(|e| e.to_string())?
-            } else {
-                return Err(format!("Provider not found: {}", provider_id));
-            }
-        } else {
-            let providers: Vec<_> = reg.list().iter().filter_map(|id| reg.get(id)).collect();
-            let search_futures: Vec<_> = providers
-                .iter()
-                .map(|p| {
-                    let query = query.clone();
-                    let provider = Arc::clone(p);
-                    async move {
-                        if provider.is_available().await {
-                            provider
-                                .search(
-                                    &query,
-                                    SearchOptions {
-                                        limit: Some(5),
-                                        page: None,
-                                    },
-                                )
-                                .await
-                                .ok()
-                        } else {
-                            None
-                        }
-                    }
+/// Whether a provider failure looks like a connectivity problem rather than a
+/// real answer (e.g. "package not found"), so cached data may stand in for it.
+fn is_network_error(err: &CogniaError) -> bool {
+    match err {
+        CogniaError::Network(_) | CogniaError::Download(_) => true,
+        CogniaError::Provider(msg) | CogniaError::Internal(msg) => {
+            let msg = msg.to_ascii_lowercase();
+            [
+                "timed out",
+                "timeout",
+                "connection",
+                "network",
+                "dns",
+                "resolve host",
+                "enotfound",
+                "eai_again",
+                "econnreset",
+                "econnrefused",
+                "offline",
+            ]
+            .iter()
+            .any(|needle| msg.contains(needle))
+        }
+        CogniaError::Io(e) => matches!(
+            e.kind(),
+            std::io::ErrorKind::TimedOut
+                | std::io::ErrorKind::ConnectionRefused
+                | std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::ConnectionAborted
+                | std::io::ErrorKind::NotConnected
+        ),
+        _ => false,
+    }
+}
+
+/// Serve `key` from the metadata cache when fresh, otherwise fetch and cache it.
+/// Network failures fall back to any cached entry (marked stale); offline mode
+/// never calls `fetch`.
+async fn fetch_with_cache<T, F, Fut>(
+    mut cache: Option<MetadataCache>,
+    key: &str,
+    ttl: i64,
+    force: bool,
+    offline: bool,
+    fetch: F,
+) -> Result<CachedResponse<T>, String>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = CogniaResult<T>>,
+{
+    let cached = match cache.as_mut() {
+        Some(cache) => cache.get::<T>(key).await.ok().flatten(),
+        None => None,
+    };
+
+    if offline {
+        return match cached {
+            Some(cached) => {
+                let stale = cached.is_stale;
+                Ok(CachedResponse {
+                    offline: true,
+                    ..CachedResponse::from_cached(cached, stale)
                 })
-                .collect();
-            let all_results = join_all(search_futures).await;
-            all_results.into_iter().flatten().flatten().collect()
+            }
+            None => Err(format!(
+                "Offline mode is enabled and no cached result exists for '{}'",
+                key
+            )),
+        };
+    }
+
+    let cached = match cached {
+        Some(cached) if !force && !cached.is_stale => {
+            return Ok(CachedResponse::from_cached(cached, false));
         }
+        other => other,
     };
 
-    // Store in cache
-    if let Ok(mut cache) = open_metadata_cache(settings.inner(), SEARCH_CACHE_TTL).await {
-        let _ = cache
-            .set_with_ttl(&cache_key, &results, SEARCH_CACHE_TTL)
-            .await;
+    match fetch().await {
+        Ok(data) => {
+            if let Some(cache) = cache.as_mut() {
+                let _ = cache.set_with_ttl(key, &data, ttl).await;
+            }
+            Ok(CachedResponse::fresh(data))
+        }
+        Err(err) if is_network_error(&err) => match cached {
+            Some(cached) => {
+                log::warn!(
+                    "Provider request failed, serving stale cache for {}: {}",
+                    key,
+                    err
+                );
+                Ok(CachedResponse {
+                    error: Some(err.to_string()),
+                    ..CachedResponse::from_cached(cached, true)
+                })
+            }
+            None => Err(err.to_string()),
+        },
+        Err(err) => Err(err.to_string()),
     }
+}
 
-    Ok(results)
+async fn is_offline_mode(settings: &SharedSettings) -> bool {
+    settings.read().await.network.offline_mode
+}
+
+fn search_cache_key(provider: Option<&str>, query: &str) -> String {
+    format!("pkg:search:{}:{}", provider.unwrap_or("all"), query)
+}
+
+fn info_cache_key(provider: Option<&str>, name: &str) -> String {
+    format!("pkg:info:{}:{}", provider.unwrap_or("auto"), name)
+}
+
+async fn search_live(
+    registry: &SharedRegistry,
+    provider: Option<&str>,
+    query: &str,
+) -> CogniaResult<Vec<PackageSummary>> {
+    let reg = registry.read().await;
+
+    if let Some(provider_id) = provider {
+        let p = reg
+            .get(provider_id)
+            .ok_or_else(|| CogniaError::ProviderNotFound(provider_id.to_string()))?;
+        return p.search(query, SearchOptions::default()).await;
+    }
+
+    let providers: Vec<_> = reg.list().iter().filter_map(|id| reg.get(id)).collect();
+    drop(reg);
+    let search_futures: Vec<_> = providers
+        .iter()
+        .map(|p| {
+            let provider = Arc::clone(p);
+            async move {
+                if provider.is_available().await {
+                    Some(
+                        provider
+                            .search(
+                                query,
+                                SearchOptions {
+                                    limit: Some(5),
+                                    page: None,
+                                },
+                            )
+                            .await,
+                    )
+                } else {
+                    None
+                }
+            }
+        })
+        .collect();
+
+    let mut results = Vec::new();
+    let mut any_ok = false;
+    let mut network_error = None;
+    for outcome in join_all(search_futures).await.into_iter().flatten() {
+        match outcome {
+            Ok(found) => {
+                any_ok = true;
+                results.extend(found);
+            }
+            Err(err) if is_network_error(&err) => network_error = Some(err),
+            Err(_) => {}
+        }
+    }
+
+    // Only a failure of every provider counts as a failed search
+    match network_error {
+        Some(err) if !any_ok => Err(err),
+        _ => Ok(results),
+    }
+}
+
+async fn info_live(
+    registry: &SharedRegistry,
+    provider: Option<&str>,
+    name: &str,
+) -> CogniaResult<PackageInfo> {
+    let reg = registry.read().await;
+    if let Some(provider_id) = provider {
+        let p = reg
+            .get(provider_id)
+            .ok_or_else(|| CogniaError::ProviderNotFound(provider_id.to_string()))?;
+        require_capability(p.as_ref(), Capability::Info, "package info")?;
+        p.get_package_info(name).await
+    } else if let Some(p) = reg.find_for_package(name).await? {
+        require_capability(p.as_ref(), Capability::Info, "package info")?;
+        p.get_package_info(name).await
+    } else {
+        Err(CogniaError::PackageNotFound(name.to_string()))
+    }
+}
+
+#[tauri::command]
+pub async fn package_search(
+    query: String,
+    provider: Option<String>,
+    force: Option<bool>,
+    registry: State<'_, SharedRegistry>,
+    settings: State<'_, SharedSettings>,
+) -> Result<CachedResponse<Vec<PackageSummary>>, String> {
+    let cache_key = search_cache_key(provider.as_deref(), &query);
+    let cache = open_metadata_cache(settings.inner(), SEARCH_CACHE_TTL)
+        .await
+        .ok();
+    let offline = is_offline_mode(settings.inner()).await;
+
+    fetch_with_cache(
+        cache,
+        &cache_key,
+        SEARCH_CACHE_TTL,
+        force.unwrap_or(false),
+        offline,
+        || search_live(registry.inner(), provider.as_deref(), &query),
+    )
+    .await
 }
 
 #[tauri::command]
@@ -198,47 +387,119 @@ pub async fn package_info(
     force: Option<bool>,
     registry: State<'_, SharedRegistry>,
     settings: State<'_, SharedSettings>,
-) -> Result<PackageInfo, String> {
-    let cache_key = format!(
-        "pkg:info:{}:{}",
-        provider.as_deref().unwrap_or("auto"),
-        &name
-    );
+) -> Result<CachedResponse<PackageInfo>, String> {
+    let cache_key = info_cache_key(provider.as_deref(), &name);
+    let cache = open_metadata_cache(settings.inner(), INFO_CACHE_TTL)
+        .await
+        .ok();
+    let offline = is_offline_mode(settings.inner()).await;
+
+    fetch_with_cache(
+        cache,
+        &cache_key,
+        INFO_CACHE_TTL,
+        force.unwrap_or(false),
+        offline,
+        || info_live(registry.inner(), provider.as_deref(), &name),
+    )
+    .await
+}
 
-    if !force.unwrap_or(false) {
-        if let Ok(mut cache) = open_metadata_cache(settings.inner(), INFO_CACHE_TTL).await {
-            if let Ok(Some(cached)) = cache.get::<PackageInfo>(&cache_key).await {
-                if !cached.is_stale {
-                    return Ok(cached.data);
-                }
-            }
-        }
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheWarmResult {
+    pub provider: String,
+    pub warmed: Vec<String>,
+    pub failed: Vec<CacheWarmFailure>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheWarmFailure {
+    pub query: String,
+    pub error: String,
+}
+
+/// Pre-populate the search and info cache for frequently used packages so they
+/// remain available offline.
+#[tauri::command]
+pub async fn provider_cache_warm(
+    provider: String,
+    queries: Vec<String>,
+    registry: State<'_, SharedRegistry>,
+    settings: State<'_, SharedSettings>,
+) -> Result<CacheWarmResult, String> {
+    if is_offline_mode(settings.inner()).await {
+        return Err("Cannot warm the cache while offline mode is enabled".into());
+    }
+    if registry.read().await.get(&provider).is_none() {
+        return Err(format!("Provider not found: {}", provider));
     }
 
-    let info = {
-        let reg = registry.read().await;
-        if let Some(provider_id) = provider.as_deref() {
-            if let Some(p) = reg.get(provider_id) {
-                p.get_package_info(&name).await.map_err(|e| e.to_string())?
-            } else {
-                return Err(format!("Provider not found: {}", provider_id));
-            }
-        } else if let Some(p) = reg
-            .find_for_package(&name)
-            .await
-            .map_err(|e| e.to_string())?
-        {
-            p.get_package_info(&name).await.map_err(|e| e.to_string())?
-        } else {
-            return Err(format!("Package not found: {}", name));
-        }
+    let mut result = CacheWarmResult {
+        provider: provider.clone(),
+        warmed: Vec::new(),
+        failed: Vec::new(),
     };
 
-    if let Ok(mut cache) = open_metadata_cache(settings.inner(), INFO_CACHE_TTL).await {
-        let _ = cache.set_with_ttl(&cache_key, &info, INFO_CACHE_TTL).await;
+    for query in queries {
+        let query = query.trim().to_string();
+        if query.is_empty() {
+            continue;
+        }
+
+        let search = fetch_with_cache(
+            open_metadata_cache(settings.inner(), SEARCH_CACHE_TTL)
+                .await
+                .ok(),
+            &search_cache_key(Some(&provider), &query),
+            SEARCH_CACHE_TTL,
+            true,
+            false,
+            || search_live(registry.inner(), Some(&provider), &query),
+        )
+        .await;
+        let info = fetch_with_cache(
+            open_metadata_cache(settings.inner(), INFO_CACHE_TTL)
+                .await
+                .ok(),
+            &info_cache_key(Some(&provider), &query),
+            INFO_CACHE_TTL,
+            true,
+            false,
+            || info_live(registry.inner(), Some(&provider), &query),
+        )
+        .await;
+
+        match (search, info) {
+            (Ok(search), Ok(info)) if !search.stale && !info.stale => result.warmed.push(query),
+            (Err(error), _) | (_, Err(error)) => {
+                result.failed.push(CacheWarmFailure { query, error })
+            }
+            (Ok(search), Ok(info)) => result.failed.push(CacheWarmFailure {
+                query,
+                error: search
+                    .error
+                    .or(info.error)
+                    .unwrap_or_else(|| "provider unreachable".into()),
+            }),
+        }
     }
 
-    Ok(info)
+    Ok(result)
+}
+
+/// Query the provider's live registry API once and report whether the
+/// response still has the shape the provider parses.
+#[tauri::command]
+pub async fn provider_selftest(
+    provider: String,
+    settings: State<'_, SharedSettings>,
+) -> Result<ProviderSelftestReport, String> {
+    if is_offline_mode(settings.inner()).await {
+        return Err("Cannot run a provider self-test while offline mode is enabled".into());
+    }
+    run_selftest(&provider).await.map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -494,6 +755,9 @@ pub struct ProviderStatusInfo {
     pub display_name: String,
     pub installed: bool,
     pub platforms: Vec<Platform>,
+    /// Operations the provider declares support for
+    #[serde(default)]
+    pub capabilities: Vec<Capability>,
     pub scope_state: String,
     pub scope_reason: Option<String>,
     pub status: String,
@@ -502,6 +766,12 @@ pub struct ProviderStatusInfo {
     pub update_supported: bool,
     pub update_reason: Option<String>,
     pub update_reason_code: Option<String>,
+    /// Manager-specific figures, reported only for available providers
+    #[serde(default)]
+    pub health: Vec<ProviderHealthMetric>,
+    /// Operations that need admin rights, for system package managers
+    #[serde(default)]
+    pub elevated_operations: Vec<String>,
 }
 
 fn build_provider_status_info(
@@ -534,6 +804,7 @@ fn build_provider_status_info(
         display_name: info.display_name,
         installed,
         platforms: supported_platforms,
+        capabilities: info.capabilities,
         scope_state: scope.as_health_scope_state().to_string(),
         scope_reason: runtime_reason.as_ref().map(|r| r.code.to_string()),
         status: if scope.is_available() {
@@ -554,6 +825,8 @@ fn build_provider_status_info(
         } else {
             update_support.as_ref().map(|r| r.code.to_string())
         },
+        health: Vec::new(),
+        elevated_operations: Vec::new(),
     }
 }
 
@@ -563,12 +836,31 @@ fn provider_status_is_available(status: &ProviderStatusInfo) -> bool {
         && status.status == SUPPORT_STATUS_SUPPORTED
 }
 
+/// Provider ids reported unavailable by the last cached `provider_status_all` run.
+///
+/// Returns an empty set when no fresh status snapshot exists.
+pub(crate) async fn cached_unavailable_providers(settings: &SharedSettings) -> HashSet<String> {
+    let Ok(mut cache) = open_metadata_cache(settings, STATUS_CACHE_TTL).await else {
+        return HashSet::new();
+    };
+    match cache.get::<Vec<ProviderStatusInfo>>("pkg:status_all").await {
+        Ok(Some(cached)) if !cached.is_stale => cached
+            .data
+            .into_iter()
+            .filter(|status| !provider_status_is_available(status))
+            .map(|status| status.id)
+            .collect(),
+        _ => HashSet::new(),
+    }
+}
+
 async fn resolve_provider_status_info(
     info: crate::provider::ProviderInfo,
     provider: Option<Arc<dyn Provider>>,
     is_api_provider: bool,
     platform: Platform,
 ) -> ProviderStatusInfo {
+    let mut health = Vec::new();
     let (availability, runtime_reason_override) = if !info.enabled {
         (ProviderAvailabilityProbe::Unavailable, None)
     } else {
@@ -576,7 +868,13 @@ async fn resolve_provider_status_info(
             Some(provider) => {
                 let timeout = provider_health_probe_timeout(&info.id, is_api_provider);
                 match tokio::time::timeout(timeout, provider.is_available()).await {
-                    Ok(true) => (ProviderAvailabilityProbe::Available, None),
+                    Ok(true) => {
+                        // Health figures get the same budget; slow ones are dropped
+                        health = tokio::time::timeout(timeout, provider.health_metrics())
+                            .await
+                            .unwrap_or_default();
+                        (ProviderAvailabilityProbe::Available, None)
+                    }
                     Ok(false) => (
                         ProviderAvailabilityProbe::Unavailable,
                         provider.unavailable_reason().await,
@@ -588,7 +886,18 @@ async fn resolve_provider_status_info(
         }
     };
 
-    build_provider_status_info(info, platform, availability, runtime_reason_override)
+    let mut status =
+        build_provider_status_info(info, platform, availability, runtime_reason_override);
+    status.health = health;
+    status
+}
+
+/// Operations needing elevation, empty for providers that are not system
+/// package managers.
+fn provider_elevated_operations(reg: &ProviderRegistry, id: &str) -> Vec<String> {
+    reg.get_system_provider(id)
+        .map(|provider| provider.elevated_operations())
+        .unwrap_or_default()
 }
 
 #[tauri::command]
@@ -597,17 +906,20 @@ pub async fn provider_status(
     registry: State<'_, SharedRegistry>,
 ) -> Result<ProviderStatusInfo, String> {
     let platform = current_platform();
-    let (info, provider, is_api_provider) = {
+    let (info, provider, is_api_provider, elevated_operations) = {
         let reg = registry.read().await;
         let info = reg
             .get_provider_info(&provider_id)
             .ok_or_else(|| format!("Provider not found: {}", provider_id))?;
         let provider = reg.get(&info.id);
         let is_api_provider = reg.get_api_provider_config(&info.id).is_some();
-        (info, provider, is_api_provider)
+        let elevated_operations = provider_elevated_operations(&reg, &info.id);
+        (info, provider, is_api_provider, elevated_operations)
     };
 
-    Ok(resolve_provider_status_info(info, provider, is_api_provider, platform).await)
+    let mut status = resolve_provider_status_info(info, provider, is_api_provider, platform).await;
+    status.elevated_operations = elevated_operations;
+    Ok(status)
 }
 
 #[tauri::command]
@@ -637,16 +949,23 @@ pub async fn provider_status_all(
             .map(|info| {
                 let provider = reg.get(&info.id);
                 let is_api_provider = reg.get_api_provider_config(&info.id).is_some();
-                (info, provider, is_api_provider)
+                let elevated_operations = provider_elevated_operations(&reg, &info.id);
+                (info, provider, is_api_provider, elevated_operations)
             })
             .collect();
         drop(reg);
 
         let futures = to_check
             .into_iter()
-            .map(|(info, provider, is_api_provider)| {
-                resolve_provider_status_info(info, provider, is_api_provider, platform)
-            })
+            .map(
+                |(info, provider, is_api_provider, elevated_operations)| async move {
+                    let mut status =
+                        resolve_provider_status_info(info, provider, is_api_provider, platform)
+                            .await;
+                    status.elevated_operations = elevated_operations;
+                    status
+                },
+            )
             .collect::<Vec<_>>();
         join_all(futures).await
     };
@@ -721,20 +1040,19 @@ pub async fn package_versions(
 
     let versions = {
         let reg = registry.read().await;
-        if let Some(ref provider_id) = provider {
-            if let Some(p) = reg.get(provider_id.as_str()) {
-                p.get_versions(&name)
-                    .await
-                    .map(|v| v.into_iter().map(|vi| vi.version).collect())
-                    .map_err(|e| e.to_string())?
-            } else {
-                return Err(format!("Provider not found: {}", provider_id));
-            }
-        } else if let Some(p) = reg
-            .find_for_package(&name)
-            .await
-            .map_err(|e| e.to_string())?
-        {
+        let p = if let Some(ref provider_id) = provider {
+            Some(
+                reg.get(provider_id.as_str())
+                    .ok_or_else(|| format!("Provider not found: {}", provider_id))?,
+            )
+        } else {
+            reg.find_for_package(&name)
+                .await
+                .map_err(|e| e.to_string())?
+        };
+        if let Some(p) = p {
+            require_capability(p.as_ref(), Capability::Versions, "version listing")
+                .map_err(|e| e.to_string())?;
             p.get_versions(&name)
                 .await
                 .map(|v| v.into_iter().map(|vi| vi.version).collect())
@@ -811,10 +1129,12 @@ pub async fn provider_set_priority(
 #[cfg(test)]
 mod tests {
     use super::{
-        build_provider_status_info, dedupe_installed_packages, provider_status_is_available,
-        resolve_provider_status_info,
+        build_provider_status_info, dedupe_installed_packages, fetch_with_cache, is_network_error,
+        provider_status_is_available, resolve_provider_status_info,
     };
+    use crate::cache::MetadataCache;
     use crate::config::Settings;
+    use crate::error::CogniaError;
     use crate::platform::env::{current_platform, Platform};
     use crate::provider::support::ProviderAvailabilityProbe;
     use crate::provider::support::{
@@ -831,6 +1151,7 @@ fn installed_pkg(provider: &str, name: &str, version: &str) -> InstalledPackage
             install_path: PathBuf::from(format!("node_modules/{}", name)),
             installed_at: String::new(),
             is_global: true,
+            kind: None,
         }
     }
 
@@ -1068,4 +1389,184 @@ fn provider_status_is_available_rejects_timeout_and_unsupported_states() {
         assert!(!provider_status_is_available(&timeout_status));
         assert!(!provider_status_is_available(&unsupported_status));
     }
+
+    async fn open_cache(dir: &std::path::Path) -> Option<MetadataCache> {
+        Some(MetadataCache::open(dir).await.unwrap())
+    }
+
+    #[test]
+    fn test_is_network_error_classification() {
+        assert!(is_network_error(&CogniaError::Network("dns".into())));
+        assert!(is_network_error(&CogniaError::Provider(
+            "npm ERR! request to https://registry.npmjs.org failed, reason: getaddrinfo ENOTFOUND"
+                .into()
+        )));
+        assert!(!is_network_error(&CogniaError::PackageNotFound(
+            "left-pad".into()
+        )));
+        assert!(!is_network_error(&CogniaError::Provider(
+            "invalid package name".into()
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_with_cache_serves_fresh_entry_without_fetching() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = fetch_with_cache(
+            open_cache(dir.path()).await,
+            "k",
+            300,
+            false,
+            false,
+            || async { Ok(vec!["a".to_string()]) },
+        )
+        .await
+        .unwrap();
+        assert!(!first.from_cache);
+
+        let second = fetch_with_cache::<Vec<String>, _, _>(
+            open_cache(dir.path()).await,
+            "k",
+            300,
+            false,
+            false,
+            || async {
+                Err(CogniaError::Internal(
+                    "fresh cache entry must not trigger a fetch".into(),
+                ))
+            },
+        )
+        .await
+        .unwrap();
+        assert!(second.from_cache);
+        assert!(!second.stale);
+        assert!(second.cached_at.is_some());
+        assert_eq!(second.data, vec!["a".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_with_cache_refetches_after_ttl_expiry() {
+        let dir = tempfile::tempdir().unwrap();
+        // Negative TTL: the entry is expired as soon as it is written
+        fetch_with_cache(
+            open_cache(dir.path()).await,
+            "k",
+            -1,
+            false,
+            false,
+            || async { Ok("old".to_string()) },
+        )
+        .await
+        .unwrap();
+
+        let refreshed = fetch_with_cache(
+            open_cache(dir.path()).await,
+            "k",
+            300,
+            false,
+            false,
+            || async { Ok("new".to_string()) },
+        )
+        .await
+        .unwrap();
+        assert!(!refreshed.from_cache);
+        assert!(!refreshed.stale);
+        assert_eq!(refreshed.data, "new");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_with_cache_returns_stale_on_network_error() {
+        let dir = tempfile::tempdir().unwrap();
+        fetch_with_cache(
+            open_cache(dir.path()).await,
+            "k",
+            -1,
+            false,
+            false,
+            || async { Ok("cached".to_string()) },
+        )
+        .await
+        .unwrap();
+
+        let result = fetch_with_cache::<String, _, _>(
+            open_cache(dir.path()).await,
+            "k",
+            300,
+            false,
+            false,
+            || async { Err(CogniaError::Network("connection reset".into())) },
+        )
+        .await
+        .unwrap();
+        assert!(result.stale);
+        assert!(result.from_cache);
+        assert_eq!(result.data, "cached");
+        assert!(result.error.unwrap().contains("connection reset"));
+
+        // Real failures are not masked by the cache
+        let err = fetch_with_cache::<String, _, _>(
+            open_cache(dir.path()).await,
+            "k",
+            300,
+            true,
+            false,
+            || async { Err(CogniaError::PackageNotFound("k".into())) },
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("Package not found"));
+
+        // Network error without any cached data still fails
+        let err = fetch_with_cache::<String, _, _>(
+            open_cache(dir.path()).await,
+            "missing",
+            300,
+            false,
+            false,
+            || async { Err(CogniaError::Network("offline".into())) },
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("Network error"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_with_cache_offline_mode_never_fetches() {
+        let dir = tempfile::tempdir().unwrap();
+        fetch_with_cache(
+            open_cache(dir.path()).await,
+            "k",
+            -1,
+            false,
+            false,
+            || async { Ok(1u32) },
+        )
+        .await
+        .unwrap();
+
+        let offline = fetch_with_cache::<u32, _, _>(
+            open_cache(dir.path()).await,
+            "k",
+            300,
+            true,
+            true,
+            || async { Err(CogniaError::Internal("offline mode must not fetch".into())) },
+        )
+        .await
+        .unwrap();
+        assert!(offline.offline);
+        assert!(offline.stale);
+        assert_eq!(offline.data, 1);
+
+        let missing = fetch_with_cache::<u32, _, _>(
+            open_cache(dir.path()).await,
+            "other",
+            300,
+            false,
+            true,
+            || async { Err(CogniaError::Internal("offline mode must not fetch".into())) },
+        )
+        .await;
+        assert!(missing.unwrap_err().contains("Offline mode"));
+    }
 }
@@ -0,0 +1,142 @@
+use crate::commands::package::{invalidate_package_caches, SharedRegistry, SharedSettings};
+use crate::core::package_try::{self, PackageTry, DEFAULT_TRY_TTL_HOURS, MAX_TRY_TTL_HOURS};
+use crate::core::Orchestrator;
+use crate::platform::env::ShellType;
+use crate::platform::fs;
+use crate::provider::InstallRequest;
+use serde::Serialize;
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageTryInfo {
+    #[serde(flatten)]
+    pub entry: PackageTry,
+    /// Shell commands for the user's shell that activate the try-prefix
+    pub activation_script: String,
+    pub expired: bool,
+}
+
+impl From<PackageTry> for PackageTryInfo {
+    fn from(entry: PackageTry) -> Self {
+        Self {
+            activation_script: entry
+                .env_modifications()
+                .to_shell_commands(ShellType::detect()),
+            expired: entry.is_expired(chrono::Utc::now()),
+            entry,
+        }
+    }
+}
+
+/// Install a package into a throwaway prefix under the cognia root. The
+/// prefix is deleted by the cache cleanup task once `ttl_hours` have passed.
+#[tauri::command]
+pub async fn package_try(
+    provider: String,
+    name: String,
+    version: Option<String>,
+    ttl_hours: Option<u64>,
+    registry: State<'_, SharedRegistry>,
+    settings: State<'_, SharedSettings>,
+) -> Result<PackageTryInfo, String> {
+    let ttl_hours = ttl_hours.unwrap_or(DEFAULT_TRY_TTL_HOURS);
+    if ttl_hours == 0 || ttl_hours > MAX_TRY_TTL_HOURS {
+        return Err(format!(
+            "TTL must be between 1 and {} hours",
+            MAX_TRY_TTL_HOURS
+        ));
+    }
+    let root_dir = settings.read().await.get_root_dir();
+    let provider_impl = registry
+        .read()
+        .await
+        .get(&provider)
+        .ok_or_else(|| format!("Provider not found: {}", provider))?;
+
+    let id = package_try::new_try_id(&name);
+    let prefix = package_try::try_root(&root_dir).join(&id);
+    fs::create_dir_all(&prefix)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let request = InstallRequest {
+        name,
+        version,
+        global: false,
+        force: false,
+    };
+    let receipt = match provider_impl.install_to_prefix(request, &prefix).await {
+        Ok(receipt) => receipt,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&prefix).await;
+            return Err(e.to_string());
+        }
+    };
+
+    let entry = PackageTry::new(id, &receipt, &prefix, ttl_hours, chrono::Utc::now());
+    if let Err(e) = package_try::register_try(&root_dir, entry.clone()).await {
+        // Nothing would ever expire an unregistered prefix
+        let _ = fs::remove_dir_all(&prefix).await;
+        return Err(e.to_string());
+    }
+
+    Ok(entry.into())
+}
+
+/// List try-prefixes, newest first, including ones awaiting cleanup
+#[tauri::command]
+pub async fn package_try_list(
+    settings: State<'_, SharedSettings>,
+) -> Result<Vec<PackageTryInfo>, String> {
+    let root_dir = settings.read().await.get_root_dir();
+    let tries = package_try::list_tries(&root_dir)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(tries.into_iter().map(PackageTryInfo::from).collect())
+}
+
+/// Install a tried package into the real environment, then drop its
+/// try-prefix. The try used the provider's normal download cache, so the
+/// real install reuses the artifacts fetched for it.
+#[tauri::command]
+pub async fn package_try_promote(
+    id: String,
+    registry: State<'_, SharedRegistry>,
+    settings: State<'_, SharedSettings>,
+) -> Result<Vec<String>, String> {
+    let cloned_settings = settings.read().await.clone();
+    let root_dir = cloned_settings.get_root_dir();
+    let entry = package_try::get_try(&root_dir, &id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let spec = entry.promote_spec();
+    let orchestrator = Orchestrator::new(registry.inner().clone(), cloned_settings);
+    let receipts = orchestrator
+        .install(&[spec])
+        .await
+        .map_err(|e| e.to_string())?;
+
+    invalidate_package_caches(settings.inner()).await;
+    package_try::remove_try(&root_dir, &id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(receipts
+        .into_iter()
+        .map(|r| format!("{}@{}", r.name, r.version))
+        .collect())
+}
+
+/// Delete a try-prefix now instead of waiting for it to expire
+#[tauri::command]
+pub async fn package_try_remove(
+    id: String,
+    settings: State<'_, SharedSettings>,
+) -> Result<bool, String> {
+    let root_dir = settings.read().await.get_root_dir();
+    package_try::remove_try(&root_dir, &id)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,398 @@
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::process::{self, ProcessOptions};
+use crate::provider::pixi::{parse_global_list, parse_list_json, PixiGlobalEnv, PixiPackage};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PixiProjectInfo {
+    pub name: Option<String>,
+    pub manifest_path: Option<String>,
+    pub version: Option<String>,
+    pub last_updated: Option<String>,
+    pub pixi_folder_size: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PixiEnvironmentInfo {
+    pub name: String,
+    pub features: Vec<String>,
+    pub solve_group: Option<String>,
+    pub environment_size: Option<String>,
+    pub dependencies: Vec<String>,
+    pub pypi_dependencies: Vec<String>,
+    pub platforms: Vec<String>,
+    pub tasks: Vec<String>,
+    pub channels: Vec<String>,
+    pub prefix: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PixiInfo {
+    pub pixi_version: Option<String>,
+    pub platform: Option<String>,
+    pub cache_dir: Option<String>,
+    pub project: Option<PixiProjectInfo>,
+    pub environments: Vec<PixiEnvironmentInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PixiRunResult {
+    pub exit_code: i32,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+// ── Helper ──
+
+fn make_opts(timeout_secs: u64) -> ProcessOptions {
+    ProcessOptions::new().with_timeout(Duration::from_secs(timeout_secs))
+}
+
+async fn ensure_pixi() -> CogniaResult<()> {
+    if process::which("pixi").await.is_none() {
+        return Err(CogniaError::Provider(
+            "pixi is not installed or not on PATH (see https://pixi.sh)".into(),
+        ));
+    }
+    Ok(())
+}
+
+async fn run_pixi(args: &[&str], timeout: u64) -> CogniaResult<String> {
+    ensure_pixi().await?;
+    let opts = make_opts(timeout);
+    let out = process::execute("pixi", args, Some(opts)).await?;
+    if out.success {
+        Ok(out.stdout)
+    } else {
+        Err(CogniaError::Provider(if out.stderr.is_empty() {
+            out.stdout
+        } else {
+            out.stderr
+        }))
+    }
+}
+
+/// Append `--manifest-path <path>` when targeting a specific project.
+fn push_manifest_arg<'a>(args: &mut Vec<&'a str>, manifest_path: &'a Option<String>) {
+    if let Some(path) = manifest_path.as_deref().filter(|p| !p.is_empty()) {
+        args.push("--manifest-path");
+        args.push(path);
+    }
+}
+
+fn json_string_list(value: &serde_json::Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn json_opt_string(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn parse_pixi_info(json: &serde_json::Value) -> PixiInfo {
+    let project = json
+        .get("project_info")
+        .filter(|v| v.is_object())
+        .map(|p| PixiProjectInfo {
+            name: json_opt_string(&p["name"]),
+            manifest_path: json_opt_string(&p["manifest_path"]),
+            version: json_opt_string(&p["version"]),
+            last_updated: json_opt_string(&p["last_updated"]),
+            pixi_folder_size: json_opt_string(&p["pixi_folder_size"]),
+        });
+
+    let environments = json["environments_info"]
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|env| {
+                    Some(PixiEnvironmentInfo {
+                        name: env["name"].as_str()?.to_string(),
+                        features: json_string_list(&env["features"]),
+                        solve_group: json_opt_string(&env["solve_group"]),
+                        environment_size: json_opt_string(&env["environment_size"]),
+                        dependencies: json_string_list(&env["dependencies"]),
+                        pypi_dependencies: json_string_list(&env["pypi_dependencies"]),
+                        platforms: json_string_list(&env["platforms"]),
+                        tasks: json_string_list(&env["tasks"]),
+                        channels: json_string_list(&env["channels"]),
+                        prefix: json_opt_string(&env["prefix"]),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    PixiInfo {
+        pixi_version: json_opt_string(&json["version"]),
+        platform: json_opt_string(&json["platform"]),
+        cache_dir: json_opt_string(&json["cache_dir"]),
+        project,
+        environments,
+    }
+}
+
+// ── Project commands ──
+
+#[tauri::command]
+pub async fn pixi_project_init(
+    path: String,
+    channels: Vec<String>,
+    platforms: Vec<String>,
+    pyproject: bool,
+) -> Result<String, String> {
+    let mut args = vec!["init", path.as_str()];
+    for channel in &channels {
+        args.push("--channel");
+        args.push(channel);
+    }
+    for platform in &platforms {
+        args.push("--platform");
+        args.push(platform);
+    }
+    if pyproject {
+        args.push("--format");
+        args.push("pyproject");
+    }
+    run_pixi(&args, 60).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn pixi_add(
+    manifest_path: Option<String>,
+    packages: Vec<String>,
+    pypi: bool,
+    feature: Option<String>,
+) -> Result<String, String> {
+    if packages.is_empty() {
+        return Err("No packages specified".into());
+    }
+    let mut args = vec!["add"];
+    push_manifest_arg(&mut args, &manifest_path);
+    if pypi {
+        args.push("--pypi");
+    }
+    if let Some(ref f) = feature {
+        args.push("--feature");
+        args.push(f);
+    }
+    args.extend(packages.iter().map(|s| s.as_str()));
+    run_pixi(&args, 600).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn pixi_remove(
+    manifest_path: Option<String>,
+    packages: Vec<String>,
+    pypi: bool,
+    feature: Option<String>,
+) -> Result<String, String> {
+    if packages.is_empty() {
+        return Err("No packages specified".into());
+    }
+    let mut args = vec!["remove"];
+    push_manifest_arg(&mut args, &manifest_path);
+    if pypi {
+        args.push("--pypi");
+    }
+    if let Some(ref f) = feature {
+        args.push("--feature");
+        args.push(f);
+    }
+    args.extend(packages.iter().map(|s| s.as_str()));
+    run_pixi(&args, 300).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn pixi_install(
+    manifest_path: Option<String>,
+    environment: Option<String>,
+    frozen: bool,
+) -> Result<String, String> {
+    let mut args = vec!["install"];
+    push_manifest_arg(&mut args, &manifest_path);
+    if let Some(ref env) = environment {
+        args.push("--environment");
+        args.push(env);
+    }
+    if frozen {
+        args.push("--frozen");
+    }
+    run_pixi(&args, 900).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn pixi_list(
+    manifest_path: Option<String>,
+    environment: Option<String>,
+) -> Result<Vec<PixiPackage>, String> {
+    let mut args = vec!["list", "--json"];
+    push_manifest_arg(&mut args, &manifest_path);
+    if let Some(ref env) = environment {
+        args.push("--environment");
+        args.push(env);
+    }
+    let out = run_pixi(&args, 120).await.map_err(|e| e.to_string())?;
+    parse_list_json(&out).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn pixi_env_info(manifest_path: Option<String>) -> Result<PixiInfo, String> {
+    let mut args = vec!["info", "--json"];
+    push_manifest_arg(&mut args, &manifest_path);
+    let out = run_pixi(&args, 60).await.map_err(|e| e.to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(&out).map_err(|e| format!("Failed to parse pixi info: {}", e))?;
+
+    Ok(parse_pixi_info(&json))
+}
+
+#[tauri::command]
+pub async fn pixi_run(
+    manifest_path: Option<String>,
+    task: String,
+    run_args: Vec<String>,
+    environment: Option<String>,
+) -> Result<PixiRunResult, String> {
+    ensure_pixi().await.map_err(|e| e.to_string())?;
+
+    let mut args = vec!["run"];
+    push_manifest_arg(&mut args, &manifest_path);
+    if let Some(ref env) = environment {
+        args.push("--environment");
+        args.push(env);
+    }
+    args.push(task.as_str());
+    args.extend(run_args.iter().map(|s| s.as_str()));
+
+    let out = process::execute("pixi", &args, Some(make_opts(600)))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(PixiRunResult {
+        exit_code: out.exit_code,
+        stdout: out.stdout,
+        stderr: out.stderr,
+    })
+}
+
+// ── Global tool commands ──
+
+#[tauri::command]
+pub async fn pixi_global_install(
+    packages: Vec<String>,
+    channels: Vec<String>,
+) -> Result<String, String> {
+    if packages.is_empty() {
+        return Err("No packages specified".into());
+    }
+    let mut args = vec!["global", "install"];
+    for channel in &channels {
+        args.push("--channel");
+        args.push(channel);
+    }
+    args.extend(packages.iter().map(|s| s.as_str()));
+    run_pixi(&args, 600).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn pixi_global_list() -> Result<Vec<PixiGlobalEnv>, String> {
+    let out = run_pixi(&["global", "list"], 60)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(parse_global_list(&out))
+}
+
+/// Remove globally installed environments (and the binaries they expose).
+#[tauri::command]
+pub async fn pixi_global_remove(environments: Vec<String>) -> Result<String, String> {
+    if environments.is_empty() {
+        return Err("No environments specified".into());
+    }
+    let mut args = vec!["global", "uninstall"];
+    args.extend(environments.iter().map(|s| s.as_str()));
+    run_pixi(&args, 120).await.map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_manifest_arg() {
+        let mut args = vec!["list"];
+        let manifest = Some("/work/pixi.toml".to_string());
+        push_manifest_arg(&mut args, &manifest);
+        assert_eq!(args, vec!["list", "--manifest-path", "/work/pixi.toml"]);
+
+        let mut args = vec!["list"];
+        let empty = Some(String::new());
+        push_manifest_arg(&mut args, &empty);
+        push_manifest_arg(&mut args, &None);
+        assert_eq!(args, vec!["list"]);
+    }
+
+    #[test]
+    fn test_parse_pixi_info() {
+        let json: serde_json::Value = serde_json::from_str(
+            r#"{
+                "platform": "linux-64",
+                "version": "0.39.5",
+                "cache_dir": "/home/u/.cache/rattler/cache",
+                "project_info": {
+                    "name": "demo",
+                    "manifest_path": "/work/pixi.toml",
+                    "last_updated": null,
+                    "pixi_folder_size": null,
+                    "version": "0.1.0"
+                },
+                "environments_info": [{
+                    "name": "default",
+                    "features": ["default"],
+                    "solve_group": null,
+                    "environment_size": null,
+                    "dependencies": ["python", "numpy"],
+                    "pypi_dependencies": ["requests"],
+                    "platforms": ["linux-64"],
+                    "tasks": ["test"],
+                    "channels": ["conda-forge"],
+                    "prefix": "/work/.pixi/envs/default"
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        let info = parse_pixi_info(&json);
+        assert_eq!(info.pixi_version.as_deref(), Some("0.39.5"));
+        let project = info.project.unwrap();
+        assert_eq!(project.name.as_deref(), Some("demo"));
+        assert!(project.last_updated.is_none());
+        assert_eq!(info.environments.len(), 1);
+        assert_eq!(info.environments[0].dependencies, vec!["python", "numpy"]);
+        assert_eq!(info.environments[0].pypi_dependencies, vec!["requests"]);
+    }
+
+    #[test]
+    fn test_parse_pixi_info_without_project() {
+        let json: serde_json::Value =
+            serde_json::from_str(r#"{"version": "0.39.5", "project_info": null}"#).unwrap();
+        let info = parse_pixi_info(&json);
+        assert!(info.project.is_none());
+        assert!(info.environments.is_empty());
+    }
+}
@@ -1,11 +1,16 @@
+use crate::plugin::dev_watch::{spawn_dev_watch, DevWatcher, PluginDevWatchInfo};
 use crate::plugin::manager::{
     CapabilityAuditRecord, PluginActionReport, PluginHealth, PluginManager, PluginUpdateInfo,
 };
 use crate::plugin::manifest::PluginManifest;
+use crate::plugin::marketplace::{
+    MarketplaceFilter, MarketplaceIndexSummary, MarketplaceInstallReport, MarketplacePluginListing,
+};
 use crate::plugin::permissions::PermissionEnforcementMode;
 use crate::plugin::permissions::PluginPermissionState;
 use crate::plugin::registry::{PluginInfo, PluginToolInfo};
 use crate::plugin::scaffold::{ScaffoldConfig, ScaffoldResult, ValidationResult};
+use crate::plugin::storage::{PluginDataDirInfo, PluginStorageInspection};
 use serde::Serialize;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -380,6 +385,44 @@ pub async fn plugin_reload(
     mgr.reload(&plugin_id).await.map_err(|e| e.to_string())
 }
 
+/// Watch a local plugin's built WASM (and manifest) and hot-reload it on change
+#[tauri::command]
+pub async fn plugin_dev_watch(
+    plugin_id: String,
+    wasm_path: String,
+    manager: State<'_, SharedPluginManager>,
+    app: AppHandle,
+) -> Result<PluginDevWatchInfo, String> {
+    ensure_plugin_manager_initialized(&manager).await?;
+    let mut mgr = manager.write().await;
+    let info = mgr
+        .prepare_dev_watch(&plugin_id, &PathBuf::from(wasm_path))
+        .await
+        .map_err(|e| e.to_string())?;
+    let task = spawn_dev_watch(manager.inner().clone(), app, info.clone());
+    mgr.start_dev_watch(DevWatcher::new(info.clone(), task));
+    Ok(info)
+}
+
+/// Stop hot-reloading a plugin. Returns whether a watch was active.
+#[tauri::command]
+pub async fn plugin_dev_unwatch(
+    plugin_id: String,
+    manager: State<'_, SharedPluginManager>,
+) -> Result<bool, String> {
+    let mut mgr = manager.write().await;
+    Ok(mgr.stop_dev_watch(&plugin_id))
+}
+
+/// List active plugin dev watches
+#[tauri::command]
+pub async fn plugin_dev_watch_list(
+    manager: State<'_, SharedPluginManager>,
+) -> Result<Vec<PluginDevWatchInfo>, String> {
+    let mgr = manager.read().await;
+    Ok(mgr.list_dev_watches())
+}
+
 /// Call a tool function on a plugin
 #[tauri::command]
 pub async fn plugin_call_tool(
@@ -542,16 +585,44 @@ pub async fn plugin_revoke_permission(
         .map_err(|e| e.to_string())
 }
 
-/// Get plugin data directory path
+/// Get plugin data directory path with storage usage vs quota
 #[tauri::command]
 pub async fn plugin_get_data_dir(
     plugin_id: String,
     manager: State<'_, SharedPluginManager>,
-) -> Result<String, String> {
+) -> Result<PluginDataDirInfo, String> {
+    ensure_plugin_manager_initialized(&manager).await?;
+    let mgr = manager.read().await;
+    mgr.get_plugin_data_dir_info(&plugin_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// List the keys a plugin has written to its key-value store
+#[tauri::command]
+pub async fn plugin_storage_inspect(
+    plugin_id: String,
+    manager: State<'_, SharedPluginManager>,
+) -> Result<PluginStorageInspection, String> {
     ensure_plugin_manager_initialized(&manager).await?;
     let mgr = manager.read().await;
-    let dir = mgr.get_plugin_data_dir(&plugin_id).await;
-    Ok(dir.display().to_string())
+    mgr.inspect_plugin_storage(&plugin_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Delete keys from a plugin's key-value store; clears everything when `keys` is omitted
+#[tauri::command]
+pub async fn plugin_storage_clear(
+    plugin_id: String,
+    keys: Option<Vec<String>>,
+    manager: State<'_, SharedPluginManager>,
+) -> Result<usize, String> {
+    ensure_plugin_manager_initialized(&manager).await?;
+    let mgr = manager.read().await;
+    mgr.clear_plugin_storage(&plugin_id, keys)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 /// Get plugin locale data for frontend i18n
@@ -863,6 +934,19 @@ pub async fn plugin_get_settings_values(
         .map_err(|e| e.to_string())
 }
 
+/// Get settings values merged with schema defaults, with schema drift flagged
+#[tauri::command]
+pub async fn plugin_get_settings_resolved(
+    plugin_id: String,
+    manager: State<'_, SharedPluginManager>,
+) -> Result<crate::plugin::manifest::ResolvedPluginSettings, String> {
+    ensure_plugin_manager_initialized(&manager).await?;
+    let mgr = manager.read().await;
+    mgr.get_plugin_settings_resolved(&plugin_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Set a single setting value for a plugin
 #[tauri::command]
 pub async fn plugin_set_setting(
@@ -875,6 +959,7 @@ pub async fn plugin_set_setting(
     let mut mgr = manager.write().await;
     mgr.set_plugin_setting(&plugin_id, &key, value)
         .await
+        .map(|_| ())
         .map_err(|e| e.to_string())
 }
 
@@ -888,6 +973,58 @@ pub async fn plugin_check_all_updates(
     Ok(mgr.check_all_updates().await)
 }
 
+/// Fetch and verify the remote marketplace index
+#[tauri::command]
+pub async fn plugin_marketplace_refresh(
+    manager: State<'_, SharedPluginManager>,
+) -> Result<MarketplaceIndexSummary, String> {
+    ensure_plugin_manager_initialized(&manager).await?;
+    let mgr = manager.read().await;
+    mgr.refresh_marketplace_index()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// List plugins from the marketplace index
+#[tauri::command]
+pub async fn plugin_marketplace_list(
+    filter: Option<MarketplaceFilter>,
+    manager: State<'_, SharedPluginManager>,
+) -> Result<Vec<MarketplacePluginListing>, String> {
+    ensure_plugin_manager_initialized(&manager).await?;
+    let mgr = manager.read().await;
+    mgr.list_marketplace(&filter.unwrap_or_default())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Install or upgrade a plugin from the marketplace index. Permissions added since
+/// the installed version stay revoked unless listed in `grant_permissions`.
+#[tauri::command]
+pub async fn plugin_marketplace_install(
+    id: String,
+    grant_permissions: Option<Vec<String>>,
+    manager: State<'_, SharedPluginManager>,
+) -> Result<MarketplaceInstallReport, String> {
+    ensure_plugin_manager_initialized(&manager).await?;
+    let mut mgr = manager.write().await;
+    mgr.install_from_marketplace_index(&id, &grant_permissions.unwrap_or_default())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Compare installed plugins against the marketplace index
+#[tauri::command]
+pub async fn plugin_check_updates(
+    manager: State<'_, SharedPluginManager>,
+) -> Result<Vec<PluginUpdateInfo>, String> {
+    ensure_plugin_manager_initialized(&manager).await?;
+    let mgr = manager.read().await;
+    mgr.check_marketplace_updates()
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Update all plugins that have available updates
 #[tauri::command]
 pub async fn plugin_update_all(
@@ -1,6 +1,9 @@
 use crate::error::{CogniaError, CogniaResult};
+use crate::platform::fs;
 use crate::platform::process::{self, ProcessOptions};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
 use std::time::Duration;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +22,81 @@ pub struct PoetryRunResult {
     pub stderr: String,
 }
 
+/// A dependency declared in `pyproject.toml`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoetryDependencySpec {
+    pub name: String,
+    /// Version constraint, or the git/path/url source for non-index deps
+    pub constraint: Option<String>,
+    pub extras: Vec<String>,
+    pub optional: bool,
+}
+
+/// A dependency group; `main` holds the regular dependencies
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoetryDependencyGroup {
+    pub name: String,
+    pub optional: bool,
+    pub dependencies: Vec<PoetryDependencySpec>,
+}
+
+/// A package from `poetry show`, with its declared constraint next to the
+/// version locked in `poetry.lock`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoetryDependencyNode {
+    pub name: String,
+    /// Constraint from `pyproject.toml` for top-level packages, from the
+    /// parent package for nested ones
+    pub constraint: Option<String>,
+    pub locked_version: Option<String>,
+    /// Group declaring a top-level package
+    pub group: Option<String>,
+    pub description: Option<String>,
+    pub dependencies: Vec<PoetryDependencyNode>,
+}
+
+/// Error from commands that read `pyproject.toml` or `poetry.lock`
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PoetryError {
+    /// The file is not valid TOML; `line` and `column` are 1-based
+    ManifestParse {
+        path: String,
+        message: String,
+        line: Option<usize>,
+        column: Option<usize>,
+    },
+    Command {
+        message: String,
+    },
+}
+
+impl std::fmt::Display for PoetryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ManifestParse {
+                path,
+                message,
+                line: Some(line),
+                column,
+            } => write!(f, "{}:{}:{}: {}", path, line, column.unwrap_or(1), message),
+            Self::ManifestParse { path, message, .. } => write!(f, "{}: {}", path, message),
+            Self::Command { message } => f.write_str(message),
+        }
+    }
+}
+
+impl From<CogniaError> for PoetryError {
+    fn from(err: CogniaError) -> Self {
+        Self::Command {
+            message: err.to_string(),
+        }
+    }
+}
+
 // ── Helper ──
 
 fn make_opts(timeout_secs: u64) -> ProcessOptions {
@@ -42,6 +120,378 @@ async fn run_poetry(args: &[&str], cwd: Option<&str>, timeout: u64) -> CogniaRes
     }
 }
 
+/// 1-based line and column of a byte offset
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map_or(0, |rest| rest.chars().count())
+        + 1;
+    (line, column)
+}
+
+fn parse_manifest(path: &Path, content: &str) -> Result<toml::Table, PoetryError> {
+    toml::from_str(content).map_err(|e: toml::de::Error| {
+        let (line, column) = match e.span() {
+            Some(span) => {
+                let (line, column) = line_column(content, span.start);
+                (Some(line), Some(column))
+            }
+            None => (None, None),
+        };
+        PoetryError::ManifestParse {
+            path: path.display().to_string(),
+            message: e.message().to_string(),
+            line,
+            column,
+        }
+    })
+}
+
+async fn read_manifest(path: &Path) -> Result<Option<toml::Table>, PoetryError> {
+    if !fs::exists(path).await {
+        return Ok(None);
+    }
+    let content = fs::read_file_string(path)
+        .await
+        .map_err(|e| PoetryError::from(CogniaError::from(e)))?;
+    parse_manifest(path, &content).map(Some)
+}
+
+async fn read_pyproject(project: &str) -> Result<toml::Table, PoetryError> {
+    let path = Path::new(project).join("pyproject.toml");
+    read_manifest(&path)
+        .await?
+        .ok_or_else(|| PoetryError::Command {
+            message: format!("pyproject.toml not found in {}", project),
+        })
+}
+
+/// PEP 503 normalized package name
+fn normalize_name(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    let mut last_separator = false;
+    for c in name.trim().chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !last_separator {
+                normalized.push('-');
+            }
+            last_separator = true;
+        } else {
+            normalized.push(c.to_ascii_lowercase());
+            last_separator = false;
+        }
+    }
+    normalized
+}
+
+fn string_list(value: Option<&toml::Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// A Poetry dependency entry: `"^1.0"` or `{ version = "^1.0", extras = [..] }`
+fn parse_poetry_dependency(name: &str, value: &toml::Value) -> PoetryDependencySpec {
+    let (constraint, extras, optional) = match value {
+        toml::Value::String(constraint) => (Some(constraint.clone()), Vec::new(), false),
+        toml::Value::Table(table) => {
+            let constraint = table
+                .get("version")
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+                .or_else(|| {
+                    ["git", "path", "url"].iter().find_map(|key| {
+                        table
+                            .get(*key)
+                            .and_then(|v| v.as_str())
+                            .map(|source| format!("{}: {}", key, source))
+                    })
+                });
+            let optional = table
+                .get("optional")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            (constraint, string_list(table.get("extras")), optional)
+        }
+        // Multiple-constraint dependencies are lists of tables
+        toml::Value::Array(items) => {
+            let constraints: Vec<&str> = items
+                .iter()
+                .filter_map(|item| item.get("version").and_then(|v| v.as_str()))
+                .collect();
+            let constraint = (!constraints.is_empty()).then(|| constraints.join(" | "));
+            (constraint, Vec::new(), false)
+        }
+        _ => (None, Vec::new(), false),
+    };
+    PoetryDependencySpec {
+        name: name.to_string(),
+        constraint,
+        extras,
+        optional,
+    }
+}
+
+/// A PEP 508 requirement such as `requests[socks]>=2.31; python_version>"3.8"`
+fn parse_pep508_dependency(requirement: &str) -> Option<PoetryDependencySpec> {
+    let requirement = requirement.split(';').next().unwrap_or("").trim();
+    let name_end = requirement
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .unwrap_or(requirement.len());
+    let name = &requirement[..name_end];
+    if name.is_empty() {
+        return None;
+    }
+    let mut rest = requirement[name_end..].trim();
+    let mut extras = Vec::new();
+    if let Some(inner) = rest.strip_prefix('[') {
+        if let Some((list, after)) = inner.split_once(']') {
+            extras = list
+                .split(',')
+                .map(|extra| extra.trim().to_string())
+                .filter(|extra| !extra.is_empty())
+                .collect();
+            rest = after.trim();
+        }
+    }
+    let constraint = rest.trim_start_matches('(').trim_end_matches(')').trim();
+    Some(PoetryDependencySpec {
+        name: name.to_string(),
+        constraint: (!constraint.is_empty()).then(|| constraint.to_string()),
+        extras,
+        optional: false,
+    })
+}
+
+fn poetry_dependency_table(table: Option<&toml::Value>) -> Vec<PoetryDependencySpec> {
+    table
+        .and_then(|v| v.as_table())
+        .map(|deps| {
+            deps.iter()
+                .filter(|(name, _)| name.as_str() != "python")
+                .map(|(name, value)| parse_poetry_dependency(name, value))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Dependency groups declared in `pyproject.toml`. Covers PEP 621
+/// `[project.dependencies]`, `[tool.poetry.dependencies]`, the legacy
+/// `dev-dependencies` table and `[tool.poetry.group.<name>]`. The
+/// `python` constraint is not a package and is left out.
+pub fn parse_dependency_groups(pyproject: &toml::Table) -> Vec<PoetryDependencyGroup> {
+    let poetry = pyproject.get("tool").and_then(|tool| tool.get("poetry"));
+
+    let mut main: Vec<PoetryDependencySpec> = pyproject
+        .get("project")
+        .and_then(|project| project.get("dependencies"))
+        .and_then(|deps| deps.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str().and_then(parse_pep508_dependency))
+                .collect()
+        })
+        .unwrap_or_default();
+    for dep in poetry_dependency_table(poetry.and_then(|p| p.get("dependencies"))) {
+        // Poetry-specific entries refine PEP 621 ones of the same name
+        main.retain(|existing| normalize_name(&existing.name) != normalize_name(&dep.name));
+        main.push(dep);
+    }
+
+    let mut groups = vec![PoetryDependencyGroup {
+        name: "main".to_string(),
+        optional: false,
+        dependencies: main,
+    }];
+
+    let legacy_dev = poetry_dependency_table(poetry.and_then(|p| p.get("dev-dependencies")));
+    if !legacy_dev.is_empty() {
+        groups.push(PoetryDependencyGroup {
+            name: "dev".to_string(),
+            optional: false,
+            dependencies: legacy_dev,
+        });
+    }
+
+    if let Some(declared) = poetry
+        .and_then(|p| p.get("group"))
+        .and_then(|g| g.as_table())
+    {
+        for (name, group) in declared {
+            let dependencies = poetry_dependency_table(group.get("dependencies"));
+            let optional = group
+                .get("optional")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            match groups.iter_mut().find(|g| &g.name == name) {
+                Some(existing) => {
+                    existing.optional = optional;
+                    existing.dependencies.extend(dependencies);
+                }
+                None => groups.push(PoetryDependencyGroup {
+                    name: name.clone(),
+                    optional,
+                    dependencies,
+                }),
+            }
+        }
+    }
+
+    groups
+}
+
+/// Locked versions from `poetry.lock`, keyed by normalized package name
+fn parse_lock_versions(lock: &toml::Table) -> HashMap<String, String> {
+    lock.get("package")
+        .and_then(|packages| packages.as_array())
+        .map(|packages| {
+            packages
+                .iter()
+                .filter_map(|package| {
+                    let name = package.get("name")?.as_str()?;
+                    let version = package.get("version")?.as_str()?;
+                    Some((normalize_name(name), version.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Split a `poetry show` line into tree depth and the remaining text.
+/// Tree prefixes are drawn in four-character steps (`│   `, `├── `).
+fn split_tree_prefix(line: &str) -> (usize, &str) {
+    let text = line.trim_start_matches(['│', '├', '└', '─', ' ', '\u{a0}']);
+    let prefix_chars = line[..line.len() - text.len()].chars().count();
+    (prefix_chars.div_ceil(4), text.trim_end())
+}
+
+fn show_node(name: &str) -> PoetryDependencyNode {
+    PoetryDependencyNode {
+        name: name.to_string(),
+        constraint: None,
+        locked_version: None,
+        group: None,
+        description: None,
+        dependencies: Vec::new(),
+    }
+}
+
+/// Parse `poetry show` or `poetry show --tree` output. Top-level lines are
+/// `name [(!)] version description`; nested lines are `name constraint`.
+pub fn parse_show_output(output: &str) -> Vec<PoetryDependencyNode> {
+    let mut roots: Vec<PoetryDependencyNode> = Vec::new();
+    // Path of child indices from the current root down to the last node
+    let mut path: Vec<usize> = Vec::new();
+
+    for line in output.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (depth, text) = split_tree_prefix(line);
+        let mut parts = text.split_whitespace().filter(|part| *part != "(!)");
+        let Some(name) = parts.next() else {
+            continue;
+        };
+
+        if depth == 0 {
+            let mut node = show_node(name);
+            node.locked_version = parts.next().map(str::to_string);
+            let description = parts.collect::<Vec<_>>().join(" ");
+            node.description = (!description.is_empty()).then_some(description);
+            roots.push(node);
+            path.clear();
+            continue;
+        }
+
+        let Some(root) = roots.last_mut() else {
+            continue;
+        };
+        path.truncate(depth - 1);
+        let mut parent = root;
+        for index in &path {
+            parent = &mut parent.dependencies[*index];
+        }
+        let constraint = text[name.len()..]
+            .replace("(circular dependency aborted here)", "")
+            .trim()
+            .to_string();
+        let mut node = show_node(name);
+        node.constraint = (!constraint.is_empty()).then_some(constraint);
+        parent.dependencies.push(node);
+        path.push(parent.dependencies.len() - 1);
+    }
+
+    roots
+}
+
+fn annotate_nodes(
+    nodes: &mut [PoetryDependencyNode],
+    declared: &HashMap<String, (String, Option<String>)>,
+    locked: &HashMap<String, String>,
+    top_level: bool,
+) {
+    for node in nodes {
+        let key = normalize_name(&node.name);
+        if top_level {
+            if let Some((group, constraint)) = declared.get(&key) {
+                node.group = Some(group.clone());
+                node.constraint = constraint.clone();
+            }
+        }
+        if let Some(version) = locked.get(&key) {
+            node.locked_version = Some(version.clone());
+        }
+        annotate_nodes(&mut node.dependencies, declared, locked, false);
+    }
+}
+
+/// Python version recorded in a venv's `pyvenv.cfg`
+fn pyvenv_python_version(cfg: &str) -> Option<String> {
+    let mut version_info = None;
+    for line in cfg.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        match key.trim() {
+            "version" => return Some(value.trim().to_string()),
+            "version_info" => {
+                // `3.12.1.final.0` -> `3.12.1`
+                let parts: Vec<&str> = value.trim().split('.').take(3).collect();
+                version_info = Some(parts.join("."));
+            }
+            _ => {}
+        }
+    }
+    version_info
+}
+
+async fn read_venv_python_version(env_path: &Path) -> Option<String> {
+    let cfg = fs::read_file_string(env_path.join("pyvenv.cfg"))
+        .await
+        .ok()?;
+    pyvenv_python_version(&cfg)
+}
+
+/// Whether Poetry uses `<project>/.venv`: `virtualenvs.in-project` is
+/// true, or unset while the folder already exists
+fn uses_in_project_venv(setting: &str, venv_exists: bool) -> bool {
+    match setting.trim() {
+        "true" => true,
+        "false" => false,
+        _ => venv_exists,
+    }
+}
+
 // ── Project management commands ──
 
 #[tauri::command]
@@ -82,6 +532,91 @@ pub async fn poetry_run(path: String, command: Vec<String>) -> Result<PoetryRunR
     })
 }
 
+#[tauri::command]
+pub async fn poetry_add(
+    path: String,
+    package: String,
+    group: Option<String>,
+    extras: Option<Vec<String>>,
+    dev: Option<bool>,
+) -> Result<String, String> {
+    if package.trim().is_empty() {
+        return Err("No package specified".into());
+    }
+    let mut args = vec!["add", package.as_str()];
+    // `--dev` is deprecated in favour of the `dev` group
+    let group = group.or_else(|| dev.unwrap_or(false).then(|| "dev".to_string()));
+    if let Some(ref g) = group {
+        args.push("--group");
+        args.push(g);
+    }
+    let extras = extras.unwrap_or_default();
+    for extra in &extras {
+        args.push("--extras");
+        args.push(extra);
+    }
+    run_poetry(&args, Some(&path), 600)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn poetry_remove(
+    path: String,
+    package: String,
+    group: Option<String>,
+) -> Result<String, String> {
+    if package.trim().is_empty() {
+        return Err("No package specified".into());
+    }
+    let mut args = vec!["remove", package.as_str()];
+    if let Some(ref g) = group {
+        args.push("--group");
+        args.push(g);
+    }
+    run_poetry(&args, Some(&path), 300)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Installed packages from `poetry show`, optionally as a dependency tree,
+/// annotated with declared constraints and `poetry.lock` versions
+#[tauri::command]
+pub async fn poetry_show(
+    path: String,
+    tree: bool,
+) -> Result<Vec<PoetryDependencyNode>, PoetryError> {
+    let pyproject = read_pyproject(&path).await?;
+    let lock = read_manifest(&Path::new(&path).join("poetry.lock")).await?;
+
+    let mut args = vec!["show"];
+    if tree {
+        args.push("--tree");
+    }
+    let out = run_poetry(&args, Some(&path), 120).await?;
+    let mut nodes = parse_show_output(&out);
+
+    let mut declared = HashMap::new();
+    for group in parse_dependency_groups(&pyproject) {
+        for dep in group.dependencies {
+            declared
+                .entry(normalize_name(&dep.name))
+                .or_insert((group.name.clone(), dep.constraint));
+        }
+    }
+    let locked = lock.as_ref().map(parse_lock_versions).unwrap_or_default();
+    annotate_nodes(&mut nodes, &declared, &locked, true);
+
+    Ok(nodes)
+}
+
+/// Dependency groups declared in the project's `pyproject.toml`
+#[tauri::command]
+pub async fn poetry_groups_list(path: String) -> Result<Vec<PoetryDependencyGroup>, PoetryError> {
+    let pyproject = read_pyproject(&path).await?;
+    Ok(parse_dependency_groups(&pyproject))
+}
+
 #[tauri::command]
 pub async fn poetry_env_list(path: String) -> Result<Vec<PoetryEnvInfo>, String> {
     let out = run_poetry(&["env", "list", "--full-path"], Some(&path), 30)
@@ -98,12 +633,34 @@ pub async fn poetry_env_list(path: String) -> Result<Vec<PoetryEnvInfo>, String>
         let env_path = line.replace("(Activated)", "").trim().to_string();
 
         envs.push(PoetryEnvInfo {
+            python_version: read_venv_python_version(Path::new(&env_path)).await,
             path: env_path,
-            python_version: None,
             is_active,
         });
     }
 
+    // In-project venvs are not always listed by `poetry env list`
+    let venv = Path::new(&path).join(".venv");
+    let setting = run_poetry(&["config", "virtualenvs.in-project"], Some(&path), 30)
+        .await
+        .unwrap_or_default();
+    if uses_in_project_venv(&setting, fs::exists(venv.join("pyvenv.cfg")).await) {
+        let venv_path = venv.display().to_string();
+        let python_version = read_venv_python_version(&venv).await;
+        envs.retain(|env| env.path != ".venv" && Path::new(&env.path) != venv.as_path());
+        for env in &mut envs {
+            env.is_active = false;
+        }
+        envs.insert(
+            0,
+            PoetryEnvInfo {
+                path: venv_path,
+                python_version,
+                is_active: true,
+            },
+        );
+    }
+
     Ok(envs)
 }
 
@@ -156,3 +713,176 @@ pub async fn poetry_version(path: String) -> Result<String, String> {
         .map(|s| s.trim().to_string())
         .map_err(|e| e.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PYPROJECT: &str = r#"
+[project]
+name = "demo"
+dependencies = ["requests[socks]>=2.31; python_version > '3.8'", "rich"]
+
+[tool.poetry.dependencies]
+python = "^3.11"
+httpx = { version = "^0.27", extras = ["http2"], optional = true }
+mylib = { git = "https://example.com/mylib.git" }
+
+[tool.poetry.dev-dependencies]
+black = "^24.0"
+
+[tool.poetry.group.docs]
+optional = true
+
+[tool.poetry.group.docs.dependencies]
+mkdocs = "*"
+
+[tool.poetry.group.test.dependencies]
+pytest = "^8.0"
+"#;
+
+    fn dep(name: &str, constraint: Option<&str>) -> PoetryDependencySpec {
+        PoetryDependencySpec {
+            name: name.to_string(),
+            constraint: constraint.map(str::to_string),
+            extras: Vec::new(),
+            optional: false,
+        }
+    }
+
+    #[test]
+    fn test_parse_dependency_groups() {
+        let table: toml::Table = toml::from_str(PYPROJECT).unwrap();
+        let groups = parse_dependency_groups(&table);
+        let names: Vec<&str> = groups.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, vec!["main", "dev", "docs", "test"]);
+
+        let main = &groups[0];
+        assert_eq!(
+            main.dependencies[0],
+            PoetryDependencySpec {
+                name: "requests".into(),
+                constraint: Some(">=2.31".into()),
+                extras: vec!["socks".into()],
+                optional: false,
+            }
+        );
+        assert_eq!(main.dependencies[1], dep("rich", None));
+        let httpx = main
+            .dependencies
+            .iter()
+            .find(|d| d.name == "httpx")
+            .unwrap();
+        assert_eq!(httpx.constraint.as_deref(), Some("^0.27"));
+        assert_eq!(httpx.extras, vec!["http2".to_string()]);
+        assert!(httpx.optional);
+        let mylib = main
+            .dependencies
+            .iter()
+            .find(|d| d.name == "mylib")
+            .unwrap();
+        assert_eq!(
+            mylib.constraint.as_deref(),
+            Some("git: https://example.com/mylib.git")
+        );
+        assert!(!main.dependencies.iter().any(|d| d.name == "python"));
+
+        assert_eq!(groups[1].dependencies, vec![dep("black", Some("^24.0"))]);
+        assert!(groups[2].optional);
+        assert_eq!(groups[3].dependencies, vec![dep("pytest", Some("^8.0"))]);
+    }
+
+    #[test]
+    fn test_parse_manifest_reports_position() {
+        let content = "[tool.poetry]\nname = \"demo\"\nversion = \n";
+        let err = parse_manifest(Path::new("pyproject.toml"), content).unwrap_err();
+        match err {
+            PoetryError::ManifestParse { line, column, .. } => {
+                assert_eq!(line, Some(3));
+                assert!(column.is_some());
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+    }
+
+    #[test]
+    fn test_line_column() {
+        let content = "ab\ncd\nef";
+        assert_eq!(line_column(content, 0), (1, 1));
+        assert_eq!(line_column(content, 4), (2, 2));
+        assert_eq!(line_column(content, 100), (3, 3));
+    }
+
+    #[test]
+    fn test_parse_show_tree_output() {
+        let output = "\
+requests 2.31.0 Python HTTP for Humans.
+├── certifi >=2017.4.17
+├── charset-normalizer >=2,<4
+│   └── idna >=2.5
+└── urllib3 >=1.21.1,<3
+rich (!) 13.7.0 Render rich text
+└── pygments >=2.13.0,<3.0.0 (circular dependency aborted here)
+";
+        let nodes = parse_show_output(output);
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].name, "requests");
+        assert_eq!(nodes[0].locked_version.as_deref(), Some("2.31.0"));
+        assert_eq!(
+            nodes[0].description.as_deref(),
+            Some("Python HTTP for Humans.")
+        );
+        assert_eq!(nodes[0].dependencies.len(), 3);
+        assert_eq!(
+            nodes[0].dependencies[1].constraint.as_deref(),
+            Some(">=2,<4")
+        );
+        assert_eq!(nodes[0].dependencies[1].dependencies[0].name, "idna");
+        assert_eq!(nodes[1].locked_version.as_deref(), Some("13.7.0"));
+        assert_eq!(
+            nodes[1].dependencies[0].constraint.as_deref(),
+            Some(">=2.13.0,<3.0.0")
+        );
+    }
+
+    #[test]
+    fn test_annotate_nodes_with_constraints_and_lock() {
+        let mut nodes = parse_show_output("Requests 2.31.0 HTTP\n└── charset_normalizer >=2,<4\n");
+        let declared = HashMap::from([(
+            "requests".to_string(),
+            ("main".to_string(), Some(">=2.31".to_string())),
+        )]);
+        let lock: toml::Table =
+            toml::from_str("[[package]]\nname = \"charset-normalizer\"\nversion = \"3.3.2\"\n")
+                .unwrap();
+        annotate_nodes(&mut nodes, &declared, &parse_lock_versions(&lock), true);
+
+        assert_eq!(nodes[0].group.as_deref(), Some("main"));
+        assert_eq!(nodes[0].constraint.as_deref(), Some(">=2.31"));
+        let child = &nodes[0].dependencies[0];
+        assert_eq!(child.constraint.as_deref(), Some(">=2,<4"));
+        assert_eq!(child.locked_version.as_deref(), Some("3.3.2"));
+        assert!(child.group.is_none());
+    }
+
+    #[test]
+    fn test_pyvenv_python_version() {
+        assert_eq!(
+            pyvenv_python_version("home = /usr/bin\nversion = 3.12.1\n").as_deref(),
+            Some("3.12.1")
+        );
+        assert_eq!(
+            pyvenv_python_version("version_info = 3.11.4.final.0\n").as_deref(),
+            Some("3.11.4")
+        );
+        assert_eq!(pyvenv_python_version("home = /usr/bin\n"), None);
+    }
+
+    #[test]
+    fn test_uses_in_project_venv() {
+        assert!(uses_in_project_venv("true\n", false));
+        assert!(!uses_in_project_venv("false", true));
+        assert!(uses_in_project_venv("null", true));
+        assert!(!uses_in_project_venv("", false));
+    }
+}
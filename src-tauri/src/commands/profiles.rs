@@ -1,9 +1,125 @@
+use crate::commands::config::SharedSettings;
+use crate::core::profiles::{
+    parse_secret_placeholder, unresolved_secret_keys, PROFILE_EXPORT_FORMAT_VERSION,
+};
+use crate::core::trash::TrashKind;
 use crate::core::{
     EnvironmentProfile, ProfileApplyResult, ProfileEnvironment, SharedProfileManager,
 };
 use crate::provider::wsl::{WslProfileApplyResult, WslProfileSnapshot, WslProvider};
+use crate::SharedRegistry;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use tauri::State;
 
+/// Availability of one profile environment on this machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileImportEnvironmentStatus {
+    pub env_type: String,
+    pub version: String,
+    pub provider_id: String,
+    pub provider_available: bool,
+    pub version_installed: bool,
+    pub issue: Option<String>,
+}
+
+/// What importing a profile payload would do, without importing it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileImportPreview {
+    pub profile_name: String,
+    pub description: Option<String>,
+    /// `None` for legacy exports that are a bare profile object
+    pub format_version: Option<u32>,
+    pub environments: Vec<ProfileImportEnvironmentStatus>,
+    /// Env types whose provider or version is unavailable here
+    pub unavailable: Vec<String>,
+    /// Redacted env snapshot keys that need values before import
+    pub missing_secrets: Vec<String>,
+    pub has_env_snapshot: bool,
+    pub has_wsl_snapshot: bool,
+    pub warnings: Vec<String>,
+}
+
+/// A profile payload that passed [`validate_profile_payload`]
+#[derive(Debug, Clone)]
+pub(crate) struct ValidatedProfilePayload {
+    pub profile: EnvironmentProfile,
+    pub format_version: Option<u32>,
+    pub missing_secrets: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Validate an exported profile, accepting both the portable envelope and the
+/// legacy bare profile JSON. Shared by `profile_import_preview` and `profile_import`.
+pub(crate) fn validate_profile_payload(json: &str) -> Result<ValidatedProfilePayload, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| format!("Invalid profile JSON: {}", e))?;
+    let object = value
+        .as_object()
+        .ok_or_else(|| "Profile payload must be a JSON object".to_string())?;
+
+    let (profile_value, format_version) = match object.get("format_version") {
+        Some(version) => {
+            let version = version
+                .as_u64()
+                .ok_or_else(|| "format_version must be a positive integer".to_string())?;
+            if version == 0 || version > PROFILE_EXPORT_FORMAT_VERSION as u64 {
+                return Err(format!(
+                    "Unsupported profile export format version {} (this version supports up to {})",
+                    version, PROFILE_EXPORT_FORMAT_VERSION
+                ));
+            }
+            let profile = object
+                .get("profile")
+                .cloned()
+                .ok_or_else(|| "Profile export is missing the 'profile' field".to_string())?;
+            (profile, Some(version as u32))
+        }
+        None => (value, None),
+    };
+
+    let profile: EnvironmentProfile =
+        serde_json::from_value(profile_value).map_err(|e| format!("Invalid profile: {}", e))?;
+
+    if profile.name.trim().is_empty() {
+        return Err("Profile name must not be empty".to_string());
+    }
+
+    let mut warnings = Vec::new();
+    let mut seen_types = HashSet::new();
+    for env in &profile.environments {
+        if env.env_type.trim().is_empty() || env.version.trim().is_empty() {
+            return Err("Profile environments require both env_type and version".to_string());
+        }
+        if !seen_types.insert(env.env_type.to_lowercase()) {
+            warnings.push(format!(
+                "Environment '{}' is listed more than once; the last entry wins",
+                env.env_type
+            ));
+        }
+    }
+
+    Ok(ValidatedProfilePayload {
+        missing_secrets: unresolved_secret_keys(&profile),
+        profile,
+        format_version,
+        warnings,
+    })
+}
+
+/// Fill redacted env snapshot placeholders from `secrets`
+fn apply_profile_secrets(profile: &mut EnvironmentProfile, secrets: &HashMap<String, String>) {
+    if let Some(snapshot) = profile.env_snapshot.as_mut() {
+        for (key, value) in snapshot.iter_mut() {
+            if parse_secret_placeholder(value).is_some() {
+                if let Some(secret) = secrets.get(key) {
+                    *value = secret.clone();
+                }
+            }
+        }
+    }
+}
+
 /// List all profiles
 #[tauri::command]
 pub async fn profile_list(
@@ -84,14 +200,31 @@ pub async fn profile_update(
     mgr.update(profile).await.map_err(|e| e.to_string())
 }
 
-/// Delete a profile
+/// Delete a profile, keeping a copy in the trash
 #[tauri::command]
 pub async fn profile_delete(
     id: String,
     manager: State<'_, SharedProfileManager>,
+    settings: State<'_, SharedSettings>,
 ) -> Result<(), String> {
+    let root_dir = settings.read().await.get_root_dir();
     let mut mgr = manager.write().await;
-    mgr.delete(&id).await.map_err(|e| e.to_string())
+    let profile = mgr
+        .get(&id)
+        .ok_or_else(|| format!("Profile with ID {} not found", id))?;
+    let entry = super::trash::trash_before_delete(
+        &root_dir,
+        TrashKind::EnvironmentProfile,
+        &profile.id,
+        &profile.name,
+        &profile,
+    )
+    .await?;
+    if let Err(e) = mgr.delete(&id).await {
+        super::trash::discard(&root_dir, &entry).await;
+        return Err(e.to_string());
+    }
+    Ok(())
 }
 
 /// Apply a profile (switch to all specified versions)
@@ -104,24 +237,128 @@ pub async fn profile_apply(
     mgr.apply(&id).await.map_err(|e| e.to_string())
 }
 
-/// Export a profile to JSON
+/// Export a profile to portable JSON, optionally redacting secret env values.
+/// Keys are matched against `envvar.secret_key_patterns` unless the call
+/// passes its own patterns.
 #[tauri::command]
 pub async fn profile_export(
     id: String,
+    redact_secrets: Option<bool>,
+    secret_patterns: Option<Vec<String>>,
     manager: State<'_, SharedProfileManager>,
+    settings: State<'_, SharedSettings>,
 ) -> Result<String, String> {
+    let patterns = match secret_patterns {
+        Some(patterns) => patterns,
+        None => settings.read().await.envvar.secret_key_patterns.clone(),
+    };
     let mgr = manager.read().await;
-    mgr.export(&id).map_err(|e| e.to_string())
+    mgr.export_portable(&id, redact_secrets.unwrap_or(false), &patterns)
+        .map_err(|e| e.to_string())
+}
+
+/// Validate a profile payload and report what importing it would require
+#[tauri::command]
+pub async fn profile_import_preview(
+    json: String,
+    manager: State<'_, SharedProfileManager>,
+    registry: State<'_, SharedRegistry>,
+) -> Result<ProfileImportPreview, String> {
+    let validated = validate_profile_payload(&json)?;
+    let mut warnings = validated.warnings;
+    let profile = validated.profile;
+
+    let mut environments = Vec::with_capacity(profile.environments.len());
+    {
+        let mgr = manager.read().await;
+        let registry = registry.read().await;
+        for env in &profile.environments {
+            let provider_id = env
+                .provider_id
+                .clone()
+                .unwrap_or_else(|| mgr.env_type_to_default_provider(&env.env_type));
+            let mut status = ProfileImportEnvironmentStatus {
+                env_type: env.env_type.clone(),
+                version: env.version.clone(),
+                provider_id: provider_id.clone(),
+                provider_available: false,
+                version_installed: false,
+                issue: None,
+            };
+
+            match registry.get_environment_provider(&provider_id) {
+                None => {
+                    status.issue = Some(format!(
+                        "Provider '{}' is not supported on this machine",
+                        provider_id
+                    ));
+                }
+                Some(provider) => {
+                    if !provider.is_available().await {
+                        status.issue = Some(format!("Provider '{}' is not installed", provider_id));
+                    } else {
+                        status.provider_available = true;
+                        match provider.list_installed_versions().await {
+                            Ok(versions) => {
+                                status.version_installed =
+                                    versions.iter().any(|v| v.version == env.version);
+                                if !status.version_installed {
+                                    status.issue =
+                                        Some(format!("Version {} is not installed", env.version));
+                                }
+                            }
+                            Err(e) => {
+                                status.issue = Some(format!("Failed to list versions: {}", e));
+                            }
+                        }
+                    }
+                }
+            }
+            environments.push(status);
+        }
+    }
+
+    if profile.wsl_snapshot.is_some() && !cfg!(target_os = "windows") {
+        warnings.push("WSL snapshots can only be applied on Windows".to_string());
+    }
+
+    Ok(ProfileImportPreview {
+        profile_name: profile.name.clone(),
+        description: profile.description.clone(),
+        format_version: validated.format_version,
+        unavailable: environments
+            .iter()
+            .filter(|status| !status.version_installed)
+            .map(|status| status.env_type.clone())
+            .collect(),
+        environments,
+        missing_secrets: validated.missing_secrets,
+        has_env_snapshot: profile.env_snapshot.is_some(),
+        has_wsl_snapshot: profile.wsl_snapshot.is_some(),
+        warnings,
+    })
 }
 
-/// Import a profile from JSON
+/// Import a profile from JSON. Redacted secrets must be supplied via `secrets`.
 #[tauri::command]
 pub async fn profile_import(
     json: String,
+    secrets: Option<HashMap<String, String>>,
     manager: State<'_, SharedProfileManager>,
 ) -> Result<EnvironmentProfile, String> {
+    let mut profile = validate_profile_payload(&json)?.profile;
+    apply_profile_secrets(&mut profile, &secrets.unwrap_or_default());
+
+    let missing = unresolved_secret_keys(&profile);
+    if !missing.is_empty() {
+        return Err(format!(
+            "Values are required for redacted secrets: {}",
+            missing.join(", ")
+        ));
+    }
+
     let mut mgr = manager.write().await;
-    mgr.import(&json).await.map_err(|e| e.to_string())
+    mgr.import_profile(profile).await.map_err(|e| e.to_string())
 }
 
 /// Create a profile from current environment state
@@ -186,3 +423,100 @@ pub async fn profile_apply_wsl_snapshot(
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn portable(version: u32, profile: serde_json::Value) -> String {
+        serde_json::json!({
+            "format_version": version,
+            "exported_at": "2026-01-01T00:00:00Z",
+            "redacted_keys": [],
+            "profile": profile,
+        })
+        .to_string()
+    }
+
+    fn profile_json() -> serde_json::Value {
+        serde_json::json!({
+            "id": "p1",
+            "name": "Dev",
+            "description": null,
+            "environments": [{"env_type": "node", "version": "20.0.0", "provider_id": null}],
+            "env_snapshot": {"API_TOKEN": "<<cognia-secret:API_TOKEN>>", "EDITOR": "vim"},
+            "created_at": "2026-01-01T00:00:00Z",
+            "updated_at": "2026-01-01T00:00:00Z"
+        })
+    }
+
+    #[test]
+    fn test_validate_profile_payload_accepts_portable_and_legacy() {
+        let validated = validate_profile_payload(&portable(1, profile_json())).unwrap();
+        assert_eq!(validated.format_version, Some(1));
+        assert_eq!(validated.missing_secrets, vec!["API_TOKEN"]);
+
+        let legacy = validate_profile_payload(&profile_json().to_string()).unwrap();
+        assert_eq!(legacy.format_version, None);
+        assert_eq!(legacy.profile.name, "Dev");
+    }
+
+    #[test]
+    fn test_validate_profile_payload_rejects_malformed() {
+        assert!(validate_profile_payload("{not json").is_err());
+        assert!(validate_profile_payload("[]")
+            .unwrap_err()
+            .contains("JSON object"));
+        assert!(validate_profile_payload(r#"{"name": "x"}"#)
+            .unwrap_err()
+            .contains("Invalid profile"));
+
+        let mut blank_name = profile_json();
+        blank_name["name"] = serde_json::json!("  ");
+        assert!(validate_profile_payload(&blank_name.to_string()).is_err());
+
+        let mut blank_version = profile_json();
+        blank_version["environments"][0]["version"] = serde_json::json!("");
+        assert!(validate_profile_payload(&blank_version.to_string()).is_err());
+
+        let missing_profile = r#"{"format_version": 1, "exported_at": "x"}"#;
+        assert!(validate_profile_payload(missing_profile)
+            .unwrap_err()
+            .contains("'profile'"));
+    }
+
+    #[test]
+    fn test_validate_profile_payload_rejects_version_mismatch() {
+        let future = portable(PROFILE_EXPORT_FORMAT_VERSION + 1, profile_json());
+        assert!(validate_profile_payload(&future)
+            .unwrap_err()
+            .contains("Unsupported profile export format version"));
+        assert!(validate_profile_payload(&portable(0, profile_json())).is_err());
+        let non_numeric = r#"{"format_version": "1", "profile": {}}"#;
+        assert!(validate_profile_payload(non_numeric).is_err());
+    }
+
+    #[test]
+    fn test_validate_profile_payload_warns_on_duplicate_env_types() {
+        let mut profile = profile_json();
+        profile["environments"] = serde_json::json!([
+            {"env_type": "node", "version": "18.0.0", "provider_id": null},
+            {"env_type": "Node", "version": "20.0.0", "provider_id": null}
+        ]);
+        let validated = validate_profile_payload(&profile.to_string()).unwrap();
+        assert_eq!(validated.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_apply_profile_secrets_fills_placeholders() {
+        let mut profile = validate_profile_payload(&profile_json().to_string())
+            .unwrap()
+            .profile;
+        apply_profile_secrets(
+            &mut profile,
+            &HashMap::from([("API_TOKEN".to_string(), "s3cret".to_string())]),
+        );
+        assert!(unresolved_secret_keys(&profile).is_empty());
+        assert_eq!(profile.env_snapshot.unwrap()["API_TOKEN"], "s3cret");
+    }
+}
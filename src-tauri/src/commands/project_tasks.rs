@@ -0,0 +1,105 @@
+use crate::commands::launch::{
+    launch_with_streaming, LaunchRequest, LaunchResult, SharedRegistry, SharedSettings,
+};
+use crate::core::env_disk_usage::EnvUsageLog;
+use crate::core::launch_monitor::SharedLaunchMonitor;
+use crate::core::project_tasks::{list_project_tasks, ProjectTask};
+use crate::core::EnvironmentManager;
+use std::collections::HashMap;
+use std::path::Path;
+use tauri::{AppHandle, State};
+
+/// List the runnable tasks declared in a project: package.json scripts,
+/// Makefile targets, justfile recipes and Taskfile tasks
+#[tauri::command]
+pub async fn project_tasks_list(path: String) -> Result<Vec<ProjectTask>, String> {
+    list_project_tasks(Path::new(&path))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Run a task from [`project_tasks_list`] in the project directory, with
+/// output streamed as `command-output` events. Node tasks run under the
+/// version the project pins, when it pins one; Makefile, justfile and
+/// Taskfile tasks run with every environment the project pins activated.
+#[tauri::command]
+pub async fn project_task_run(
+    path: String,
+    task_id: String,
+    app: AppHandle,
+    registry: State<'_, SharedRegistry>,
+    monitor: State<'_, SharedLaunchMonitor>,
+    settings: State<'_, SharedSettings>,
+) -> Result<LaunchResult, String> {
+    let task = list_project_tasks(Path::new(&path))
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|task| task.id == task_id)
+        .ok_or_else(|| format!("Task '{}' not found in {}", task_id, path))?;
+
+    // Only activate an environment the project actually pins
+    let manager = EnvironmentManager::new(registry.inner().clone());
+    let mut env = None;
+    let mut extra_env = None;
+    if let Some(env_type) = &task.env_type {
+        if let Ok(Some(detected)) = manager.detect_version(env_type, Path::new(&path)).await {
+            env = Some((env_type.clone(), detected.version));
+        }
+    } else {
+        // Generic runners may call into any toolchain the project uses
+        extra_env = pinned_environment_vars(&manager, Path::new(&path)).await;
+    }
+    let (env_type, env_version) = env.unzip();
+
+    let request = LaunchRequest {
+        program: task.program,
+        args: task.args,
+        cwd: Some(path),
+        env_type,
+        env_version,
+        extra_env,
+        timeout_secs: None,
+        profile: Some(task.id),
+        limits: None,
+    };
+    launch_with_streaming(request, app, registry, monitor, settings).await
+}
+
+/// Variables that activate every environment pinned in `dir`, with their
+/// PATH entries ahead of the inherited PATH. Environments that only resolve
+/// to the global version, or whose pinned version is not installed, are
+/// left as they are.
+async fn pinned_environment_vars(
+    manager: &EnvironmentManager,
+    dir: &Path,
+) -> Option<HashMap<String, String>> {
+    let detected = manager.detect_all_versions(dir).await.ok()?;
+    let mut vars = HashMap::new();
+    let mut path_prepend = Vec::new();
+    let mut path_append = Vec::new();
+    for env in detected.iter().filter(|env| env.source_type != "global") {
+        let Ok(mods) = manager
+            .get_env_modifications(&env.env_type, &env.version, None)
+            .await
+        else {
+            continue;
+        };
+        EnvUsageLog::record(&env.env_type, &env.version, Some(dir)).await;
+        path_prepend.extend(mods.path_prepend);
+        path_append.extend(mods.path_append);
+        vars.extend(mods.set_variables);
+    }
+
+    if !path_prepend.is_empty() || !path_append.is_empty() {
+        let mut paths = path_prepend;
+        if let Some(current) = std::env::var_os("PATH") {
+            paths.extend(std::env::split_paths(&current));
+        }
+        paths.extend(path_append);
+        if let Ok(joined) = std::env::join_paths(paths) {
+            vars.insert("PATH".to_string(), joined.to_string_lossy().to_string());
+        }
+    }
+    (!vars.is_empty()).then_some(vars)
+}
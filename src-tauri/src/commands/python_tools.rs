@@ -0,0 +1,385 @@
+use crate::commands::config::SharedSettings;
+use crate::core::python_tools::{
+    self, PythonTool, PythonToolBackend, PythonToolBackendChoice, PYTHON_TOOL_SHIM_ENV_TYPE,
+};
+use crate::core::{ShimConfig, ShimManager};
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::{fs, process};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PythonToolInstallResult {
+    pub tool: PythonTool,
+    /// Shims created or updated for the tool's binaries
+    pub shims: Vec<String>,
+    /// Binaries left without a shim because another environment owns the name
+    pub skipped_shims: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PythonToolMigrateFailure {
+    pub name: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PythonToolMigrateResult {
+    pub from: PythonToolBackend,
+    pub to: PythonToolBackend,
+    pub migrated: Vec<String>,
+    /// Tools left on the source backend
+    pub failed: Vec<PythonToolMigrateFailure>,
+}
+
+// ── Helper ──
+
+async fn run_backend(
+    backend: PythonToolBackend,
+    args: &[&str],
+    timeout: u64,
+) -> CogniaResult<String> {
+    let opts = process::ProcessOptions::new().with_timeout(Duration::from_secs(timeout));
+    let out = process::execute(backend.as_str(), args, Some(opts)).await?;
+    if out.success {
+        Ok(out.stdout)
+    } else {
+        Err(CogniaError::Provider(if out.stderr.is_empty() {
+            out.stdout
+        } else {
+            out.stderr
+        }))
+    }
+}
+
+async fn available_backends() -> Vec<PythonToolBackend> {
+    let mut backends = Vec::new();
+    for backend in [PythonToolBackend::Uv, PythonToolBackend::Pipx] {
+        if process::which(backend.as_str()).await.is_some() {
+            backends.push(backend);
+        }
+    }
+    backends
+}
+
+async fn resolve_backend(
+    requested: PythonToolBackendChoice,
+    settings: &SharedSettings,
+) -> CogniaResult<PythonToolBackend> {
+    let preferred = settings.read().await.general.python_tool_backend;
+    let available = available_backends().await;
+    python_tools::select_backend(
+        requested,
+        preferred,
+        available.contains(&PythonToolBackend::Uv),
+        available.contains(&PythonToolBackend::Pipx),
+    )
+}
+
+async fn list_backend(backend: PythonToolBackend) -> CogniaResult<Vec<PythonTool>> {
+    match backend {
+        PythonToolBackend::Pipx => {
+            python_tools::parse_pipx_list(&run_backend(backend, &["list", "--json"], 60).await?)
+        }
+        PythonToolBackend::Uv => {
+            let output = run_backend(backend, &["tool", "list", "--show-paths"], 60).await?;
+            let mut tools = python_tools::parse_uv_tool_list(&output);
+            for tool in &mut tools {
+                if let Some(venv) = &tool.venv_path {
+                    tool.python_version = fs::read_file_string(Path::new(venv).join("pyvenv.cfg"))
+                        .await
+                        .ok()
+                        .and_then(|cfg| python_tools::parse_pyvenv_version(&cfg));
+                }
+            }
+            Ok(tools)
+        }
+    }
+}
+
+async fn find_tool(backend: PythonToolBackend, name: &str) -> CogniaResult<Option<PythonTool>> {
+    let wanted = python_tools::normalize_tool_name(name);
+    Ok(list_backend(backend)
+        .await?
+        .into_iter()
+        .find(|tool| python_tools::normalize_tool_name(&tool.name) == wanted))
+}
+
+/// Python the backend creates new tool environments with
+async fn backend_python_version(backend: PythonToolBackend) -> Option<String> {
+    let python = match backend {
+        PythonToolBackend::Uv => run_backend(backend, &["python", "find"], 30).await,
+        PythonToolBackend::Pipx => {
+            run_backend(
+                backend,
+                &["environment", "--value", "PIPX_DEFAULT_PYTHON"],
+                30,
+            )
+            .await
+        }
+    }
+    .ok()?;
+    let out = process::execute(python.trim(), &["--version"], None)
+        .await
+        .ok()?;
+    let text = if out.stdout.trim().is_empty() {
+        out.stderr
+    } else {
+        out.stdout
+    };
+    text.trim()
+        .strip_prefix("Python ")
+        .map(|v| v.trim().to_string())
+}
+
+/// Install `name` with `backend`, turning build failures into an
+/// explanation of what is missing
+async fn install_on(
+    backend: PythonToolBackend,
+    name: &str,
+    version: Option<&str>,
+) -> CogniaResult<()> {
+    let spec = python_tools::install_spec(name, version);
+    let args: Vec<&str> = match backend {
+        PythonToolBackend::Uv => vec!["tool", "install", spec.as_str()],
+        PythonToolBackend::Pipx => vec!["install", spec.as_str()],
+    };
+    match run_backend(backend, &args, 900).await {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            let output = e.to_string();
+            let python = backend_python_version(backend).await;
+            Err(
+                match python_tools::build_failure_hint(name, &output, python.as_deref()) {
+                    Some(hint) => CogniaError::Installation(format!("{}\n\n{}", hint, output)),
+                    None => e,
+                },
+            )
+        }
+    }
+}
+
+async fn uninstall_on(backend: PythonToolBackend, name: &str) -> CogniaResult<()> {
+    let args: Vec<&str> = match backend {
+        PythonToolBackend::Uv => vec!["tool", "uninstall", name],
+        PythonToolBackend::Pipx => vec!["uninstall", name],
+    };
+    run_backend(backend, &args, 120).await.map(|_| ())
+}
+
+/// Point shims at the tool's binaries and drop shims of binaries in
+/// `previous` that the tool no longer has. Names shimmed by another
+/// environment are left alone and returned as skipped.
+async fn sync_tool_shims(
+    root: &Path,
+    tool: &PythonTool,
+    previous: &[String],
+) -> CogniaResult<(Vec<String>, Vec<String>)> {
+    let mut manager = ShimManager::new(root).await?;
+    let mut shims = Vec::new();
+    let mut skipped = Vec::new();
+    for bin in tool.bins.iter().filter(|bin| !bin.path.is_empty()) {
+        let owned_elsewhere = manager
+            .get_shim(&bin.name)
+            .is_some_and(|shim| shim.env_type != PYTHON_TOOL_SHIM_ENV_TYPE);
+        if owned_elsewhere {
+            skipped.push(bin.name.clone());
+            continue;
+        }
+        manager
+            .create_shim(ShimConfig {
+                env_type: PYTHON_TOOL_SHIM_ENV_TYPE.to_string(),
+                binary_name: bin.name.clone(),
+                version: tool.version.clone(),
+                target_path: PathBuf::from(&bin.path),
+                direct: true,
+                version_switch: false,
+            })
+            .await?;
+        shims.push(bin.name.clone());
+    }
+    let current = tool.bin_names();
+    let stale: Vec<&String> = previous.iter().filter(|n| !current.contains(n)).collect();
+    remove_tool_shims(&mut manager, stale).await?;
+    Ok((shims, skipped))
+}
+
+async fn remove_tool_shims<'a>(
+    manager: &mut ShimManager,
+    names: impl IntoIterator<Item = &'a String>,
+) -> CogniaResult<()> {
+    for name in names {
+        let ours = manager
+            .get_shim(name)
+            .is_some_and(|shim| shim.env_type == PYTHON_TOOL_SHIM_ENV_TYPE);
+        if ours {
+            manager.remove_shim(name).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn installed_result(
+    backend: PythonToolBackend,
+    name: &str,
+    previous: &[String],
+    settings: &SharedSettings,
+) -> CogniaResult<PythonToolInstallResult> {
+    let tool = find_tool(backend, name).await?.ok_or_else(|| {
+        CogniaError::Installation(format!("{} did not report {} as installed", backend, name))
+    })?;
+    let root = settings.read().await.get_root_dir();
+    let (shims, skipped_shims) = sync_tool_shims(&root, &tool, previous).await?;
+    Ok(PythonToolInstallResult {
+        tool,
+        shims,
+        skipped_shims,
+    })
+}
+
+/// Backend that currently has `name` installed
+async fn locate_tool(name: &str) -> CogniaResult<PythonTool> {
+    for backend in available_backends().await {
+        if let Some(tool) = find_tool(backend, name).await? {
+            return Ok(tool);
+        }
+    }
+    Err(CogniaError::PackageNotFound(name.to_string()))
+}
+
+// ── Commands ──
+
+/// Install a Python CLI into its own environment through uv or pipx and
+/// shim its binaries. `backend` defaults to `general.python_tool_backend`.
+#[tauri::command]
+pub async fn python_tool_install(
+    name: String,
+    version: Option<String>,
+    backend: Option<PythonToolBackendChoice>,
+    settings: State<'_, SharedSettings>,
+) -> Result<PythonToolInstallResult, String> {
+    let backend = resolve_backend(backend.unwrap_or_default(), settings.inner())
+        .await
+        .map_err(|e| e.to_string())?;
+    install_on(backend, &name, version.as_deref())
+        .await
+        .map_err(|e| e.to_string())?;
+    installed_result(backend, &name, &[], settings.inner())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Tools installed through every available backend
+#[tauri::command]
+pub async fn python_tool_list() -> Result<Vec<PythonTool>, String> {
+    let mut tools = Vec::new();
+    for backend in available_backends().await {
+        tools.extend(list_backend(backend).await.map_err(|e| e.to_string())?);
+    }
+    tools.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(tools)
+}
+
+#[tauri::command]
+pub async fn python_tool_upgrade(
+    name: String,
+    settings: State<'_, SharedSettings>,
+) -> Result<PythonToolInstallResult, String> {
+    let tool = locate_tool(&name).await.map_err(|e| e.to_string())?;
+    let args: Vec<&str> = match tool.backend {
+        PythonToolBackend::Uv => vec!["tool", "upgrade", tool.name.as_str()],
+        PythonToolBackend::Pipx => vec!["upgrade", tool.name.as_str()],
+    };
+    run_backend(tool.backend, &args, 900)
+        .await
+        .map_err(|e| e.to_string())?;
+    installed_result(
+        tool.backend,
+        &tool.name,
+        &tool.bin_names(),
+        settings.inner(),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn python_tool_uninstall(
+    name: String,
+    settings: State<'_, SharedSettings>,
+) -> Result<(), String> {
+    let tool = locate_tool(&name).await.map_err(|e| e.to_string())?;
+    uninstall_on(tool.backend, &tool.name)
+        .await
+        .map_err(|e| e.to_string())?;
+    let root = settings.read().await.get_root_dir();
+    let mut manager = ShimManager::new(&root).await.map_err(|e| e.to_string())?;
+    remove_tool_shims(&mut manager, &tool.bin_names())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Move every tool from one backend to the other at the same version.
+/// A tool that fails to install on the target is reinstalled on the
+/// source and reported as failed.
+#[tauri::command]
+pub async fn python_tool_migrate(
+    backend_from: PythonToolBackend,
+    backend_to: PythonToolBackend,
+    settings: State<'_, SharedSettings>,
+) -> Result<PythonToolMigrateResult, String> {
+    if backend_from == backend_to {
+        return Err("Source and target backend are the same".into());
+    }
+    let available = available_backends().await;
+    for backend in [backend_from, backend_to] {
+        if !available.contains(&backend) {
+            return Err(format!("{} is not installed", backend));
+        }
+    }
+
+    let tools = list_backend(backend_from)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut result = PythonToolMigrateResult {
+        from: backend_from,
+        to: backend_to,
+        migrated: Vec::new(),
+        failed: Vec::new(),
+    };
+    for tool in tools {
+        let version = tool.version.as_deref();
+        let outcome = match uninstall_on(backend_from, &tool.name).await {
+            Err(e) => Err(e),
+            Ok(()) => match install_on(backend_to, &tool.name, version).await {
+                Ok(()) => Ok(backend_to),
+                Err(e) => {
+                    // Best effort: the failure is reported either way
+                    let _ = install_on(backend_from, &tool.name, version).await;
+                    Err(e)
+                }
+            },
+        };
+        let backend = match &outcome {
+            Ok(backend) => *backend,
+            Err(_) => backend_from,
+        };
+        // Shims follow wherever the tool ended up
+        let synced = installed_result(backend, &tool.name, &tool.bin_names(), settings.inner())
+            .await
+            .map(|_| ());
+        match outcome.and(synced) {
+            Ok(()) => result.migrated.push(tool.name),
+            Err(e) => result.failed.push(PythonToolMigrateFailure {
+                name: tool.name,
+                error: e.to_string(),
+            }),
+        }
+    }
+    Ok(result)
+}
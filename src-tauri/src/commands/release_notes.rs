@@ -0,0 +1,34 @@
+use crate::cache::MetadataCache;
+use crate::commands::github::make_github_provider;
+use crate::commands::package::SharedSettings;
+use crate::core::release_notes::{self, ReleaseNotes, ReleaseNotesTarget, RELEASE_NOTES_CACHE_TTL};
+use crate::SharedSecretVault;
+use tauri::State;
+
+/// Release notes for the versions after `from_version` up to `to_version` of
+/// an environment (`env_type`) or a package (`provider` and `package`).
+/// Unresolvable sources come back as link-only entries, not errors.
+#[tauri::command]
+pub async fn release_notes_get(
+    env_type: Option<String>,
+    provider: Option<String>,
+    package: Option<String>,
+    from_version: String,
+    to_version: String,
+    settings: State<'_, SharedSettings>,
+    vault: State<'_, SharedSecretVault>,
+) -> Result<ReleaseNotes, String> {
+    let target = match (env_type, provider, package) {
+        (Some(env_type), None, None) => ReleaseNotesTarget::Env { env_type },
+        (None, Some(provider), Some(package)) => ReleaseNotesTarget::Package { provider, package },
+        _ => return Err("Pass either envType, or provider and package".into()),
+    };
+    let cache_dir = settings.read().await.get_cache_dir();
+    let mut cache = MetadataCache::open_with_ttl(&cache_dir, RELEASE_NOTES_CACHE_TTL)
+        .await
+        .map_err(|e| e.to_string())?;
+    let github = make_github_provider(None, &vault).await;
+    release_notes::get_release_notes(&target, &from_version, &to_version, &mut cache, &github)
+        .await
+        .map_err(|e| e.to_string())
+}
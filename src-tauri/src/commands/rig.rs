@@ -0,0 +1,12 @@
+use crate::provider::rig::RigProvider;
+use crate::provider::Provider;
+
+/// Install rig with the platform's package manager or release tarball
+#[tauri::command]
+pub async fn rig_self_install() -> Result<String, String> {
+    let provider = RigProvider::new();
+    if provider.is_available().await {
+        return Err("rig is already installed".into());
+    }
+    provider.self_install().await.map_err(|e| e.to_string())
+}
@@ -0,0 +1,93 @@
+use crate::provider::scoop::{ScoopBucket, ScoopProvider};
+use crate::SharedRegistry;
+use tauri::State;
+
+/// Helper to get the ScoopProvider from the registry.
+async fn get_scoop(registry: &State<'_, SharedRegistry>) -> Result<ScoopProvider, String> {
+    let reg = registry.read().await;
+    if reg.get("scoop").is_none() {
+        return Err("scoop provider not registered".into());
+    }
+    Ok(ScoopProvider::new())
+}
+
+// ── Bucket management ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub async fn scoop_bucket_list(
+    registry: State<'_, SharedRegistry>,
+) -> Result<Vec<ScoopBucket>, String> {
+    let provider = get_scoop(&registry).await?;
+    provider.bucket_list().await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn scoop_bucket_add(
+    name: String,
+    repo: Option<String>,
+    registry: State<'_, SharedRegistry>,
+) -> Result<(), String> {
+    let provider = get_scoop(&registry).await?;
+    provider
+        .bucket_add(&name, repo.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn scoop_bucket_remove(
+    name: String,
+    registry: State<'_, SharedRegistry>,
+) -> Result<(), String> {
+    let provider = get_scoop(&registry).await?;
+    provider
+        .bucket_remove(&name)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// ── Maintenance ─────────────────────────────────────────────────────────
+
+#[tauri::command]
+pub async fn scoop_cleanup(
+    app: Option<String>,
+    cache: Option<bool>,
+    global: Option<bool>,
+    registry: State<'_, SharedRegistry>,
+) -> Result<(), String> {
+    let provider = get_scoop(&registry).await?;
+    provider
+        .cleanup(
+            app.as_deref(),
+            cache.unwrap_or(false),
+            global.unwrap_or(false),
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn scoop_hold(
+    name: String,
+    global: Option<bool>,
+    registry: State<'_, SharedRegistry>,
+) -> Result<(), String> {
+    let provider = get_scoop(&registry).await?;
+    provider
+        .hold(&name, global.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn scoop_unhold(
+    name: String,
+    global: Option<bool>,
+    registry: State<'_, SharedRegistry>,
+) -> Result<(), String> {
+    let provider = get_scoop(&registry).await?;
+    provider
+        .unhold(&name, global.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
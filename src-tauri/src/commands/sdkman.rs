@@ -0,0 +1,24 @@
+use crate::provider::install_script::InstallScript;
+use crate::provider::sdkman::{self, SdkmanProvider, SdkmanSelfInstall};
+use crate::provider::Provider;
+
+/// Download the SDKMAN installer so the user can review it and its SHA-256
+#[tauri::command]
+pub async fn sdkman_install_script_preview() -> Result<InstallScript, String> {
+    sdkman::install_script_preview()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Install SDKMAN with the official installer, provided it still matches
+/// the reviewed `expected_sha256`, and report whether shell profiles source
+/// its init script
+#[tauri::command]
+pub async fn sdkman_self_install(expected_sha256: String) -> Result<SdkmanSelfInstall, String> {
+    if SdkmanProvider::java().is_available().await {
+        return Err("SDKMAN is already installed".into());
+    }
+    sdkman::self_install(&expected_sha256)
+        .await
+        .map_err(|e| e.to_string())
+}
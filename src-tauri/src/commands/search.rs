@@ -1,11 +1,18 @@
 use crate::cache::MetadataCache;
+use crate::commands::config::collect_config_list;
+use crate::commands::download::{DownloadTaskInfo, SharedDownloadManager};
 use crate::config::Settings;
+use crate::core::EnvironmentManager;
 use crate::provider::{InstalledPackage, PackageSummary, ProviderRegistry, SearchOptions};
 use crate::resolver::Version;
+use crate::CancellationTokens;
+use futures::future::join_all;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 use tauri::State;
 use tokio::sync::RwLock;
 
@@ -543,10 +550,536 @@ pub async fn compare_packages(
     })
 }
 
+// ============================================================================
+// Global Search
+// ============================================================================
+
+/// Shared deadline for all sources of one `global_search` call
+const GLOBAL_SEARCH_DEADLINE: Duration = Duration::from_millis(1500);
+/// How long a `global_search` response is reused for an identical query
+const GLOBAL_SEARCH_CACHE_TTL: Duration = Duration::from_secs(10);
+/// Number of recent `global_search` responses kept in memory
+const GLOBAL_SEARCH_CACHE_SIZE: usize = 16;
+const GLOBAL_SEARCH_DEFAULT_LIMIT: usize = 20;
+const GLOBAL_SEARCH_MAX_LIMIT: usize = 100;
+/// Packages fetched per provider; the merged list is ranked and truncated afterwards
+const GLOBAL_SEARCH_PACKAGES_PER_PROVIDER: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GlobalSearchKind {
+    Package,
+    Environment,
+    Download,
+    Setting,
+}
+
+impl GlobalSearchKind {
+    const ALL: [GlobalSearchKind; 4] = [
+        GlobalSearchKind::Package,
+        GlobalSearchKind::Environment,
+        GlobalSearchKind::Download,
+        GlobalSearchKind::Setting,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            GlobalSearchKind::Package => "package",
+            GlobalSearchKind::Environment => "environment",
+            GlobalSearchKind::Download => "download",
+            GlobalSearchKind::Setting => "setting",
+        }
+    }
+}
+
+/// A normalized search hit from any source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlobalSearchResult {
+    pub kind: GlobalSearchKind,
+    pub id: String,
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub score: f64,
+}
+
+/// Outcome of one source: `ok`, `timeout`, `cancelled`, or `error`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlobalSearchSourceStatus {
+    pub kind: GlobalSearchKind,
+    pub status: String,
+    pub error: Option<String>,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlobalSearchResponse {
+    pub query: String,
+    pub results: Vec<GlobalSearchResult>,
+    pub sources: Vec<GlobalSearchSourceStatus>,
+    pub cached: bool,
+}
+
+struct CachedGlobalSearch {
+    key: String,
+    stored_at: Instant,
+    response: GlobalSearchResponse,
+}
+
+fn global_search_cache() -> &'static Mutex<VecDeque<CachedGlobalSearch>> {
+    static CACHE: OnceLock<Mutex<VecDeque<CachedGlobalSearch>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(VecDeque::with_capacity(GLOBAL_SEARCH_CACHE_SIZE)))
+}
+
+fn global_search_cache_key(query: &str, limit: usize, kinds: &[GlobalSearchKind]) -> String {
+    let kinds = kinds
+        .iter()
+        .map(|kind| kind.as_str())
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("{}|{}|{}", query.trim().to_lowercase(), limit, kinds)
+}
+
+fn global_search_cache_get(key: &str) -> Option<GlobalSearchResponse> {
+    let mut cache = global_search_cache().lock().ok()?;
+    cache.retain(|entry| entry.stored_at.elapsed() < GLOBAL_SEARCH_CACHE_TTL);
+    cache
+        .iter()
+        .find(|entry| entry.key == key)
+        .map(|entry| entry.response.clone())
+}
+
+fn global_search_cache_put(key: String, response: &GlobalSearchResponse) {
+    let Ok(mut cache) = global_search_cache().lock() else {
+        return;
+    };
+    cache.retain(|entry| entry.key != key);
+    if cache.len() >= GLOBAL_SEARCH_CACHE_SIZE {
+        cache.pop_front();
+    }
+    cache.push_back(CachedGlobalSearch {
+        key,
+        stored_at: Instant::now(),
+        response: response.clone(),
+    });
+}
+
+fn global_search_cancel_key(request_id: &str, kind: GlobalSearchKind) -> String {
+    format!("global-search:{}:{}", request_id, kind.as_str())
+}
+
+/// Score `candidate` against `query`: exact > prefix > substring > fuzzy subsequence.
+///
+/// Returns `None` when the query characters do not appear in order.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<f64> {
+    let query = query.trim().to_lowercase();
+    let candidate = candidate.to_lowercase();
+    if query.is_empty() || candidate.is_empty() {
+        return None;
+    }
+
+    // Shorter candidates covering more of the query rank higher within a tier
+    let coverage = (query.len() as f64 / candidate.len() as f64).min(1.0);
+
+    if candidate == query {
+        return Some(100.0);
+    }
+    if candidate.starts_with(&query) {
+        return Some(80.0 + 10.0 * coverage);
+    }
+    if let Some(pos) = candidate.find(&query) {
+        let at_boundary = candidate[..pos]
+            .chars()
+            .last()
+            .map_or(true, |c| !c.is_alphanumeric());
+        let base = if at_boundary { 60.0 } else { 50.0 };
+        return Some(base + 10.0 * coverage);
+    }
+
+    let mut remaining = candidate.char_indices();
+    let mut first = None;
+    let mut last = 0;
+    for qc in query.chars() {
+        let (idx, _) = remaining.by_ref().find(|(_, c)| *c == qc)?;
+        first.get_or_insert(idx);
+        last = idx;
+    }
+    let span = (last - first.unwrap_or(0) + 1) as f64;
+    let density = (query.len() as f64 / span).min(1.0);
+    Some(20.0 + 20.0 * density)
+}
+
+/// Score a hit by its title, falling back to a discounted subtitle match
+fn score_hit(query: &str, title: &str, subtitle: Option<&str>) -> Option<f64> {
+    fuzzy_score(query, title).or_else(|| {
+        subtitle
+            .and_then(|subtitle| fuzzy_score(query, subtitle))
+            .map(|score| score * 0.5)
+    })
+}
+
+fn rank_global_results(results: &mut Vec<GlobalSearchResult>, limit: usize) {
+    results.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.kind.cmp(&b.kind))
+            .then_with(|| a.title.len().cmp(&b.title.len()))
+            .then_with(|| a.title.cmp(&b.title))
+    });
+    results.truncate(limit);
+}
+
+fn make_hit(
+    query: &str,
+    kind: GlobalSearchKind,
+    id: String,
+    title: String,
+    subtitle: Option<String>,
+) -> Option<GlobalSearchResult> {
+    let score = score_hit(query, &title, subtitle.as_deref())?;
+    Some(GlobalSearchResult {
+        kind,
+        id,
+        title,
+        subtitle,
+        score,
+    })
+}
+
+/// Result of running one source: hits plus whether any part of it timed out
+type SourceOutput = Result<(Vec<GlobalSearchResult>, bool), String>;
+
+async fn search_packages_source(
+    query: &str,
+    deadline: tokio::time::Instant,
+    registry: &SharedRegistry,
+    settings: &SharedSettings,
+) -> SourceOutput {
+    let unavailable = crate::commands::package::cached_unavailable_providers(settings).await;
+    let providers: Vec<_> = {
+        let reg = registry.read().await;
+        reg.list()
+            .into_iter()
+            .filter(|id| !unavailable.contains(*id))
+            .filter_map(|id| reg.get(id))
+            .collect()
+    };
+
+    let searches = providers.into_iter().map(|provider| async move {
+        tokio::time::timeout_at(deadline, async {
+            if !provider.is_available().await {
+                return Vec::new();
+            }
+            provider
+                .search(
+                    query,
+                    SearchOptions {
+                        limit: Some(GLOBAL_SEARCH_PACKAGES_PER_PROVIDER),
+                        page: None,
+                    },
+                )
+                .await
+                .unwrap_or_default()
+        })
+        .await
+    });
+
+    let mut hits = Vec::new();
+    let mut timed_out = false;
+    for outcome in join_all(searches).await {
+        let Ok(packages) = outcome else {
+            timed_out = true;
+            continue;
+        };
+        for pkg in packages {
+            let subtitle = match pkg.latest_version.as_deref() {
+                Some(version) => format!("{} · {}", pkg.provider, version),
+                None => pkg.provider.clone(),
+            };
+            hits.extend(make_hit(
+                query,
+                GlobalSearchKind::Package,
+                format!("{}:{}", pkg.provider, pkg.name),
+                pkg.name,
+                Some(subtitle),
+            ));
+        }
+    }
+    Ok((hits, timed_out))
+}
+
+async fn search_environments_source(
+    query: &str,
+    registry: &SharedRegistry,
+    settings: &SharedSettings,
+) -> SourceOutput {
+    let environments = match crate::commands::environment::cached_env_list(settings).await {
+        Some(environments) => environments,
+        None => {
+            let max_concurrency = settings.read().await.startup.max_concurrent_scans;
+            EnvironmentManager::new(registry.clone())
+                .list_environments_with_concurrency(max_concurrency)
+                .await
+                .map_err(|e| e.to_string())?
+        }
+    };
+
+    let mut hits = Vec::new();
+    for env in environments {
+        let subtitle = match env.current_version.as_deref() {
+            Some(version) => format!("{} · {}", env.provider, version),
+            None => env.provider.clone(),
+        };
+        for installed in &env.installed_versions {
+            hits.extend(make_hit(
+                query,
+                GlobalSearchKind::Environment,
+                format!("{}@{}", env.env_type, installed.version),
+                format!("{} {}", env.env_type, installed.version),
+                Some(env.provider.clone()),
+            ));
+        }
+        hits.extend(make_hit(
+            query,
+            GlobalSearchKind::Environment,
+            env.env_type.clone(),
+            env.env_type,
+            Some(subtitle),
+        ));
+    }
+    Ok((hits, false))
+}
+
+async fn search_downloads_source(query: &str, downloads: &SharedDownloadManager) -> SourceOutput {
+    let tasks = downloads.read().await.list_tasks().await;
+    let hits = tasks
+        .iter()
+        .map(DownloadTaskInfo::from)
+        .filter_map(|task| {
+            make_hit(
+                query,
+                GlobalSearchKind::Download,
+                task.id,
+                task.name,
+                Some(format!("{} · {}", task.state, task.url)),
+            )
+        })
+        .collect();
+    Ok((hits, false))
+}
+
+async fn search_settings_source(query: &str, settings: &SharedSettings) -> SourceOutput {
+    let entries = {
+        let s = settings.read().await;
+        collect_config_list(&s)
+    };
+    let hits = entries
+        .into_iter()
+        .filter_map(|(key, value)| {
+            make_hit(
+                query,
+                GlobalSearchKind::Setting,
+                key.clone(),
+                key,
+                Some(value),
+            )
+        })
+        .collect();
+    Ok((hits, false))
+}
+
+/// Resolve once `flag` is set; polled because cancellation flags are plain atomics
+async fn wait_cancelled(flag: Option<Arc<AtomicBool>>) {
+    match flag {
+        Some(flag) => {
+            while !flag.load(AtomicOrdering::SeqCst) {
+                tokio::time::sleep(Duration::from_millis(25)).await;
+            }
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// Run one source under the shared deadline and its own cancellation flag
+async fn run_global_source<F>(
+    kind: GlobalSearchKind,
+    enabled: bool,
+    deadline: tokio::time::Instant,
+    cancel: Option<Arc<AtomicBool>>,
+    source: F,
+) -> Option<(GlobalSearchSourceStatus, Vec<GlobalSearchResult>)>
+where
+    F: std::future::Future<Output = SourceOutput>,
+{
+    if !enabled {
+        return None;
+    }
+
+    let status = |status: &str, error: Option<String>, count: usize| GlobalSearchSourceStatus {
+        kind,
+        status: status.to_string(),
+        error,
+        count,
+    };
+
+    let outcome = tokio::select! {
+        outcome = tokio::time::timeout_at(deadline, source) => outcome,
+        _ = wait_cancelled(cancel) => return Some((status("cancelled", None, 0), Vec::new())),
+    };
+
+    Some(match outcome {
+        Ok(Ok((hits, partial))) => {
+            let label = if partial { "timeout" } else { "ok" };
+            (status(label, None, hits.len()), hits)
+        }
+        Ok(Err(e)) => (status("error", Some(e), 0), Vec::new()),
+        Err(_) => (status("timeout", None, 0), Vec::new()),
+    })
+}
+
+/// Search packages, environments, downloads and settings in one call.
+///
+/// Sources run concurrently under a shared deadline; a failing, slow or
+/// cancelled source only affects its own entry in `sources`. When
+/// `request_id` is given, `global_search_cancel` can stop individual kinds.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn global_search(
+    query: String,
+    limit: Option<usize>,
+    kinds: Option<Vec<GlobalSearchKind>>,
+    request_id: Option<String>,
+    registry: State<'_, SharedRegistry>,
+    settings: State<'_, SharedSettings>,
+    downloads: State<'_, SharedDownloadManager>,
+    tokens: State<'_, CancellationTokens>,
+) -> Result<GlobalSearchResponse, String> {
+    let limit = limit
+        .unwrap_or(GLOBAL_SEARCH_DEFAULT_LIMIT)
+        .clamp(1, GLOBAL_SEARCH_MAX_LIMIT);
+    let mut kinds = kinds.unwrap_or_else(|| GlobalSearchKind::ALL.to_vec());
+    kinds.sort();
+    kinds.dedup();
+
+    let trimmed = query.trim();
+    if trimmed.is_empty() || kinds.is_empty() {
+        return Ok(GlobalSearchResponse {
+            query,
+            results: Vec::new(),
+            sources: Vec::new(),
+            cached: false,
+        });
+    }
+
+    let cache_key = global_search_cache_key(trimmed, limit, &kinds);
+    if let Some(mut cached) = global_search_cache_get(&cache_key) {
+        cached.query = query;
+        cached.cached = true;
+        return Ok(cached);
+    }
+
+    // Register one cancellation flag per kind so callers can stop them independently
+    let mut flags: HashMap<GlobalSearchKind, Arc<AtomicBool>> = HashMap::new();
+    if let Some(request_id) = request_id.as_deref() {
+        let mut guard = tokens.write().await;
+        for kind in &kinds {
+            let flag = Arc::new(AtomicBool::new(false));
+            guard.insert(global_search_cancel_key(request_id, *kind), flag.clone());
+            flags.insert(*kind, flag);
+        }
+    }
+
+    let deadline = tokio::time::Instant::now() + GLOBAL_SEARCH_DEADLINE;
+    let wants = |kind: GlobalSearchKind| kinds.contains(&kind);
+    let flag = |kind: GlobalSearchKind| flags.get(&kind).cloned();
+
+    let (packages, environments, download_hits, setting_hits) = tokio::join!(
+        run_global_source(
+            GlobalSearchKind::Package,
+            wants(GlobalSearchKind::Package),
+            deadline,
+            flag(GlobalSearchKind::Package),
+            search_packages_source(trimmed, deadline, registry.inner(), settings.inner()),
+        ),
+        run_global_source(
+            GlobalSearchKind::Environment,
+            wants(GlobalSearchKind::Environment),
+            deadline,
+            flag(GlobalSearchKind::Environment),
+            search_environments_source(trimmed, registry.inner(), settings.inner()),
+        ),
+        run_global_source(
+            GlobalSearchKind::Download,
+            wants(GlobalSearchKind::Download),
+            deadline,
+            flag(GlobalSearchKind::Download),
+            search_downloads_source(trimmed, downloads.inner()),
+        ),
+        run_global_source(
+            GlobalSearchKind::Setting,
+            wants(GlobalSearchKind::Setting),
+            deadline,
+            flag(GlobalSearchKind::Setting),
+            search_settings_source(trimmed, settings.inner()),
+        ),
+    );
+
+    if let Some(request_id) = request_id.as_deref() {
+        let mut guard = tokens.write().await;
+        for kind in &kinds {
+            guard.remove(&global_search_cancel_key(request_id, *kind));
+        }
+    }
+
+    let mut results = Vec::new();
+    let mut sources = Vec::new();
+    for (status, hits) in [packages, environments, download_hits, setting_hits]
+        .into_iter()
+        .flatten()
+    {
+        sources.push(status);
+        results.extend(hits);
+    }
+    rank_global_results(&mut results, limit);
+
+    let response = GlobalSearchResponse {
+        query,
+        results,
+        sources,
+        cached: false,
+    };
+    // Only complete responses are reused for subsequent keystrokes
+    if response.sources.iter().all(|source| source.status == "ok") {
+        global_search_cache_put(cache_key, &response);
+    }
+    Ok(response)
+}
+
+/// Cancel some or all kinds of an in-flight `global_search` call
+#[tauri::command]
+pub async fn global_search_cancel(
+    request_id: String,
+    kinds: Option<Vec<GlobalSearchKind>>,
+    tokens: State<'_, CancellationTokens>,
+) -> Result<usize, String> {
+    let kinds = kinds.unwrap_or_else(|| GlobalSearchKind::ALL.to_vec());
+    let guard = tokens.read().await;
+    let mut cancelled = 0;
+    for kind in kinds {
+        if let Some(flag) = guard.get(&global_search_cancel_key(&request_id, kind)) {
+            flag.store(true, AtomicOrdering::SeqCst);
+            cancelled += 1;
+        }
+    }
+    Ok(cancelled)
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
-        has_newer_version, installed_lookup_key, matches_filters, sort_scored_packages,
+        fuzzy_score, global_search_cache_get, global_search_cache_key, global_search_cache_put,
+        has_newer_version, installed_lookup_key, matches_filters, rank_global_results,
+        sort_scored_packages, GlobalSearchKind, GlobalSearchResponse, GlobalSearchResult,
         ScoredPackage, SearchFilters,
     };
     use crate::provider::PackageSummary;
@@ -633,4 +1166,69 @@ fn installed_lookup_key_is_provider_aware() {
             installed_lookup_key("pip", "TypeScript")
         );
     }
+
+    #[test]
+    fn fuzzy_score_ranks_prefix_over_substring_over_fuzzy() {
+        let exact = fuzzy_score("node", "node").unwrap();
+        let prefix = fuzzy_score("node", "nodemon").unwrap();
+        let boundary = fuzzy_score("node", "system-node").unwrap();
+        let substring = fuzzy_score("node", "unnoded").unwrap();
+        let fuzzy = fuzzy_score("nde", "node").unwrap();
+        assert!(exact > prefix);
+        assert!(prefix > boundary);
+        assert!(boundary > substring);
+        assert!(substring > fuzzy);
+        assert!(fuzzy_score("xyz", "node").is_none());
+        assert!(fuzzy_score("  ", "node").is_none());
+    }
+
+    #[test]
+    fn fuzzy_score_prefers_dense_subsequences() {
+        let dense = fuzzy_score("gnl", "general.language").unwrap();
+        let sparse = fuzzy_score("gnl", "git.network.timeout_long").unwrap();
+        assert!(dense > sparse);
+    }
+
+    #[test]
+    fn rank_global_results_orders_by_score_and_truncates() {
+        let hit = |kind, title: &str, score| GlobalSearchResult {
+            kind,
+            id: title.to_string(),
+            title: title.to_string(),
+            subtitle: None,
+            score,
+        };
+        let mut results = vec![
+            hit(GlobalSearchKind::Setting, "general.theme", 50.0),
+            hit(GlobalSearchKind::Package, "typescript", 90.0),
+            hit(GlobalSearchKind::Environment, "node", 90.0),
+        ];
+        rank_global_results(&mut results, 2);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].kind, GlobalSearchKind::Package);
+        assert_eq!(results[1].title, "node");
+    }
+
+    #[test]
+    fn global_search_cache_round_trips_by_normalized_key() {
+        let kinds = [GlobalSearchKind::Package, GlobalSearchKind::Setting];
+        let key = global_search_cache_key("  CacheTest-Unique ", 10, &kinds);
+        assert_eq!(key, global_search_cache_key("cachetest-unique", 10, &kinds));
+        assert!(global_search_cache_get(&key).is_none());
+
+        global_search_cache_put(
+            key.clone(),
+            &GlobalSearchResponse {
+                query: "cachetest-unique".into(),
+                results: Vec::new(),
+                sources: Vec::new(),
+                cached: false,
+            },
+        );
+        assert!(global_search_cache_get(&key).is_some());
+        assert!(
+            global_search_cache_get(&global_search_cache_key("cachetest-unique", 5, &kinds))
+                .is_none()
+        );
+    }
 }
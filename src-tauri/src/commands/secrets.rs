@@ -1,5 +1,9 @@
 use crate::config::Settings;
-use crate::secrets::{normalized_secret, provider_env_var, provider_secret_key, SecretVault};
+use crate::platform::secrets::{self as secret_store, SecretBackend, SecretRef, SecretStore};
+use crate::secrets::{
+    migrate_provider_secrets, normalized_secret, provider_env_var, provider_secret_key,
+    provider_store_key, SecretVault, PROVIDER_SECRET_NAMESPACE,
+};
 use crate::SharedSecretVault;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -21,6 +25,7 @@ pub struct SecretVaultStatus {
 pub struct ProviderSecretStatus {
     pub provider: String,
     pub configured: bool,
+    pub configured_in_store: bool,
     pub configured_in_vault: bool,
     pub configured_in_env: bool,
     pub needs_unlock: bool,
@@ -41,22 +46,35 @@ pub fn build_vault_status(settings: &Settings, vault: &SecretVault) -> SecretVau
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretStoreStatus {
+    pub backend: SecretBackend,
+}
+
 pub fn build_provider_secret_status(
     provider: &str,
     settings: &Settings,
     vault: &SecretVault,
+    configured_in_store: bool,
 ) -> ProviderSecretStatus {
     let configured_in_env = provider_env_var(provider)
         .and_then(|key| std::env::var(key).ok())
         .is_some_and(|value| !value.trim().is_empty());
     let legacy_plaintext_present = settings.get_provider_legacy_token(provider).is_some();
-    let configured_in_vault =
-        settings.get_provider_secret_saved(provider) && vault.is_initialized();
+    // A vault token is only left behind while the vault stays locked
+    let configured_in_vault = !configured_in_store
+        && settings.get_provider_secret_saved(provider)
+        && vault.is_initialized();
     let needs_unlock = configured_in_vault && !vault.is_unlocked();
 
     ProviderSecretStatus {
         provider: provider.to_string(),
-        configured: configured_in_vault || configured_in_env || legacy_plaintext_present,
+        configured: configured_in_store
+            || configured_in_vault
+            || configured_in_env
+            || legacy_plaintext_present,
+        configured_in_store,
         configured_in_vault,
         configured_in_env,
         needs_unlock,
@@ -64,16 +82,31 @@ pub fn build_provider_secret_status(
     }
 }
 
-pub fn resolve_provider_secret(
+/// Token for `provider`: explicit input, the secret store, the unlocked
+/// vault, a plaintext settings token not yet migrated, then the env var.
+pub async fn resolve_provider_secret(
     provider: &str,
     explicit_token: Option<String>,
     settings: &Settings,
     vault: &SecretVault,
+    store: &SecretStore,
 ) -> Option<String> {
     if let Some(explicit_token) = normalized_secret(explicit_token) {
         return Some(explicit_token);
     }
 
+    match store
+        .get(PROVIDER_SECRET_NAMESPACE, &provider_store_key(provider))
+        .await
+    {
+        Ok(token) => {
+            if let Some(token) = normalized_secret(token) {
+                return Some(token);
+            }
+        }
+        Err(error) => log::warn!("Failed to read the {} token: {}", provider, error),
+    }
+
     if vault.is_unlocked() {
         if let Ok(token) = vault.get_secret(&provider_secret_key(provider)) {
             if let Some(token) = normalized_secret(token) {
@@ -97,7 +130,8 @@ async fn migrate_after_unlock(
 ) -> Result<(), String> {
     let mut settings_guard = settings.write().await;
     let vault_guard = vault.write().await;
-    let migrated = vault_guard.migrate_legacy_provider_tokens(&mut settings_guard)?;
+    let migrated =
+        migrate_provider_secrets(&mut settings_guard, &vault_guard, secret_store::global()).await?;
     if !migrated.is_empty() {
         settings_guard
             .save()
@@ -107,6 +141,30 @@ async fn migrate_after_unlock(
     Ok(())
 }
 
+/// One-time move of plaintext provider tokens out of the settings file,
+/// run in the background at startup
+pub async fn migrate_plaintext_provider_secrets(
+    settings: SharedSettings,
+    vault: SharedSecretVault,
+) {
+    let mut settings_guard = settings.write().await;
+    let vault_guard = vault.read().await;
+    match migrate_provider_secrets(&mut settings_guard, &vault_guard, secret_store::global()).await
+    {
+        Ok(migrated) if !migrated.is_empty() => {
+            log::info!(
+                "Moved {} provider token(s) into the secret store",
+                migrated.len()
+            );
+            if let Err(error) = settings_guard.save().await {
+                log::warn!("Failed to save settings after token migration: {}", error);
+            }
+        }
+        Ok(_) => {}
+        Err(error) => log::warn!("Provider token migration failed: {}", error),
+    }
+}
+
 #[tauri::command]
 pub async fn secret_vault_status(
     settings: State<'_, SharedSettings>,
@@ -187,12 +245,17 @@ pub async fn provider_secret_status_internal(
     settings: &State<'_, SharedSettings>,
     vault: &State<'_, SharedSecretVault>,
 ) -> Result<ProviderSecretStatus, String> {
+    let configured_in_store = secret_store::global()
+        .contains(PROVIDER_SECRET_NAMESPACE, &provider_store_key(provider))
+        .await
+        .map_err(|error| error.to_string())?;
     let settings_guard = settings.read().await;
     let vault_guard = vault.read().await;
     Ok(build_provider_secret_status(
         provider,
         &settings_guard,
         &vault_guard,
+        configured_in_store,
     ))
 }
 
@@ -205,20 +268,21 @@ pub async fn provider_secret_save_internal(
     let token = normalized_secret(Some(token))
         .ok_or_else(|| "A non-empty token is required.".to_string())?;
 
-    {
-        let vault_guard = vault.read().await;
-        if !vault_guard.is_unlocked() {
-            return Err(
-                "Secure storage is locked. Set it up or unlock it before saving tokens."
-                    .to_string(),
-            );
-        }
-    }
+    secret_store::global()
+        .set(
+            PROVIDER_SECRET_NAMESPACE,
+            &provider_store_key(provider),
+            &token,
+        )
+        .await
+        .map_err(|error| error.to_string())?;
 
     {
         let mut settings_guard = settings.write().await;
         let vault_guard = vault.read().await;
-        vault_guard.save_secret(&provider_secret_key(provider), &token)?;
+        if vault_guard.is_unlocked() {
+            vault_guard.remove_secret(&provider_secret_key(provider))?;
+        }
         settings_guard.clear_provider_legacy_token(provider);
         settings_guard.set_provider_secret_saved(provider, true);
         settings_guard
@@ -238,10 +302,16 @@ pub async fn provider_secret_clear_internal(
     {
         let mut settings_guard = settings.write().await;
         let vault_guard = vault.read().await;
+        let removed_from_store = secret_store::global()
+            .delete(PROVIDER_SECRET_NAMESPACE, &provider_store_key(provider))
+            .await
+            .map_err(|error| error.to_string())?;
 
         if vault_guard.is_unlocked() {
             let _ = vault_guard.remove_secret(&provider_secret_key(provider))?;
-        } else if settings_guard.get_provider_secret_saved(provider) && vault_guard.is_initialized()
+        } else if !removed_from_store
+            && settings_guard.get_provider_secret_saved(provider)
+            && vault_guard.is_initialized()
         {
             return Err(
                 "Secure storage is locked. Unlock it before clearing saved tokens.".to_string(),
@@ -259,41 +329,130 @@ pub async fn provider_secret_clear_internal(
     provider_secret_status_internal(provider, settings, vault).await
 }
 
+#[tauri::command]
+pub async fn secret_store_status() -> Result<SecretStoreStatus, String> {
+    Ok(SecretStoreStatus {
+        backend: secret_store::global().backend().await,
+    })
+}
+
+/// Namespaces the backend reads and writes itself, through commands that
+/// keep the settings in step. Their values never go to the webview.
+const BACKEND_SECRET_NAMESPACES: &[&str] = &[PROVIDER_SECRET_NAMESPACE];
+
+fn ensure_frontend_namespace(namespace: &str) -> Result<(), String> {
+    if BACKEND_SECRET_NAMESPACES.contains(&namespace) {
+        return Err(format!(
+            "Secrets in '{}' are managed by the app and cannot be accessed directly",
+            namespace
+        ));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn secret_set(
+    namespace: String,
+    key: String,
+    value: String,
+) -> Result<SecretRef, String> {
+    ensure_frontend_namespace(&namespace)?;
+    secret_store::global()
+        .set(&namespace, &key, &value)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+pub async fn secret_get(namespace: String, key: String) -> Result<Option<String>, String> {
+    ensure_frontend_namespace(&namespace)?;
+    secret_store::global()
+        .get(&namespace, &key)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+pub async fn secret_delete(namespace: String, key: String) -> Result<bool, String> {
+    ensure_frontend_namespace(&namespace)?;
+    secret_store::global()
+        .delete(&namespace, &key)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+/// Secrets under `namespace`, without their values
+#[tauri::command]
+pub async fn secret_list(namespace: String) -> Result<Vec<SecretRef>, String> {
+    secret_store::global()
+        .list(&namespace)
+        .await
+        .map_err(|error| error.to_string())
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{build_provider_secret_status, build_vault_status, resolve_provider_secret};
+    use super::{
+        build_provider_secret_status, build_vault_status, ensure_frontend_namespace,
+        resolve_provider_secret,
+    };
     use crate::config::Settings;
-    use crate::secrets::{provider_secret_key, SecretVault};
+    use crate::platform::secrets::SecretStore;
+    use crate::secrets::{
+        provider_secret_key, provider_store_key, SecretVault, PROVIDER_SECRET_NAMESPACE,
+    };
     use tempfile::tempdir;
 
     #[tokio::test]
-    async fn resolve_provider_secret_prefers_explicit_then_vault_then_legacy_then_env() {
+    async fn resolve_provider_secret_prefers_explicit_then_store_then_vault_then_legacy_then_env() {
         let temp = tempdir().unwrap();
         let mut vault = SecretVault::new(temp.path().to_path_buf());
+        let store = SecretStore::encrypted_file(temp.path().to_path_buf());
         let mut settings = Settings::default();
         vault.setup("secret-passphrase").await.unwrap();
         vault
             .save_secret(&provider_secret_key("github"), "ghp_saved")
             .unwrap();
+        store
+            .set(
+                PROVIDER_SECRET_NAMESPACE,
+                &provider_store_key("github"),
+                "ghp_stored",
+            )
+            .await
+            .unwrap();
 
         std::env::set_var("GITHUB_TOKEN", "ghp_env");
         settings
             .set_provider_legacy_token("github", "ghp_legacy")
             .unwrap();
 
-        let resolved =
-            resolve_provider_secret("github", Some("ghp_explicit".into()), &settings, &vault);
+        let resolved = resolve_provider_secret(
+            "github",
+            Some("ghp_explicit".into()),
+            &settings,
+            &vault,
+            &store,
+        )
+        .await;
         assert_eq!(resolved.as_deref(), Some("ghp_explicit"));
 
-        let resolved = resolve_provider_secret("github", None, &settings, &vault);
+        let resolved = resolve_provider_secret("github", None, &settings, &vault, &store).await;
+        assert_eq!(resolved.as_deref(), Some("ghp_stored"));
+
+        store
+            .delete(PROVIDER_SECRET_NAMESPACE, &provider_store_key("github"))
+            .await
+            .unwrap();
+        let resolved = resolve_provider_secret("github", None, &settings, &vault, &store).await;
         assert_eq!(resolved.as_deref(), Some("ghp_saved"));
 
         vault.lock();
-        let resolved = resolve_provider_secret("github", None, &settings, &vault);
+        let resolved = resolve_provider_secret("github", None, &settings, &vault, &store).await;
         assert_eq!(resolved.as_deref(), Some("ghp_legacy"));
 
         settings.clear_provider_legacy_token("github");
-        let resolved = resolve_provider_secret("github", None, &settings, &vault);
+        let resolved = resolve_provider_secret("github", None, &settings, &vault, &store).await;
         assert_eq!(resolved.as_deref(), Some("ghp_env"));
     }
 
@@ -307,11 +466,23 @@ async fn provider_status_reports_locked_saved_secret_without_plaintext() {
         vault.setup("secret-passphrase").await.unwrap();
         vault.lock();
 
-        let status = build_provider_secret_status("github", &settings, &vault);
+        let status = build_provider_secret_status("github", &settings, &vault, false);
         assert!(status.configured);
         assert!(status.configured_in_vault);
         assert!(status.needs_unlock);
         assert!(!status.configured_in_env);
+
+        let status = build_provider_secret_status("github", &settings, &vault, true);
+        assert!(status.configured);
+        assert!(status.configured_in_store);
+        assert!(!status.configured_in_vault);
+        assert!(!status.needs_unlock);
+    }
+
+    #[test]
+    fn frontend_cannot_reach_provider_tokens() {
+        assert!(ensure_frontend_namespace(PROVIDER_SECRET_NAMESPACE).is_err());
+        assert!(ensure_frontend_namespace("registries").is_ok());
     }
 
     #[tokio::test]
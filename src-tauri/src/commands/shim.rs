@@ -1,8 +1,9 @@
 use crate::config::Settings;
-use crate::core::{PathManager, ShimConfig, ShimManager};
+use crate::core::trash::TrashKind;
+use crate::core::{InitShell, PathManager, ShimConfig, ShimManager};
 use crate::platform::fs;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tauri::State;
 use tokio::sync::RwLock;
@@ -25,6 +26,13 @@ pub struct PathStatus {
     pub shim_dir: String,
     pub is_in_path: bool,
     pub add_command: String,
+    pub shell: InitShell,
+    pub config_file: Option<String>,
+    pub profile_configured: bool,
+}
+
+fn parse_init_shell(shell_type: &str) -> Result<InitShell, String> {
+    InitShell::from_id(shell_type).ok_or_else(|| format!("Unsupported shell: {}", shell_type))
 }
 
 /// Create a new shim for a binary
@@ -34,6 +42,7 @@ pub async fn shim_create(
     env_type: String,
     version: Option<String>,
     target_path: String,
+    version_switch: Option<bool>,
     settings: State<'_, SharedSettings>,
 ) -> Result<String, String> {
     let settings = settings.read().await;
@@ -48,6 +57,8 @@ pub async fn shim_create(
         binary_name: binary_name.clone(),
         version,
         target_path: PathBuf::from(target_path),
+        direct: false,
+        version_switch: version_switch.unwrap_or(false),
     };
 
     let shim_path = manager
@@ -58,7 +69,7 @@ pub async fn shim_create(
     Ok(shim_path.display().to_string())
 }
 
-/// Remove a shim
+/// Remove a shim, keeping its configuration in the trash
 #[tauri::command]
 pub async fn shim_remove(
     binary_name: String,
@@ -71,19 +82,33 @@ pub async fn shim_remove(
         .await
         .map_err(|e| e.to_string())?;
 
-    manager
+    let entry = match manager.get_shim(&binary_name) {
+        Some(config) => Some(
+            super::trash::trash_before_delete(
+                &base_dir,
+                TrashKind::Shim,
+                &config.binary_name,
+                &config.binary_name,
+                config,
+            )
+            .await?,
+        ),
+        None => None,
+    };
+
+    let result = manager
         .remove_shim(&binary_name)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string());
+    if let (Err(_), Some(entry)) = (&result, &entry) {
+        super::trash::discard(&base_dir, entry).await;
+    }
+    result
 }
 
-/// List all shims
-#[tauri::command]
-pub async fn shim_list(settings: State<'_, SharedSettings>) -> Result<Vec<ShimInfo>, String> {
-    let settings = settings.read().await;
-    let base_dir = settings.get_root_dir();
-
-    let manager = ShimManager::new(&base_dir)
+/// Shims registered under the cognia root `base_dir`
+pub(crate) async fn list_shim_infos(base_dir: &Path) -> Result<Vec<ShimInfo>, String> {
+    let manager = ShimManager::new(base_dir)
         .await
         .map_err(|e| e.to_string())?;
 
@@ -105,6 +130,13 @@ pub async fn shim_list(settings: State<'_, SharedSettings>) -> Result<Vec<ShimIn
     Ok(shims)
 }
 
+/// List all shims
+#[tauri::command]
+pub async fn shim_list(settings: State<'_, SharedSettings>) -> Result<Vec<ShimInfo>, String> {
+    let base_dir = settings.read().await.get_root_dir();
+    list_shim_infos(&base_dir).await
+}
+
 /// Update a shim to point to a new version
 #[tauri::command]
 pub async fn shim_update(
@@ -145,11 +177,15 @@ pub async fn path_status(settings: State<'_, SharedSettings>) -> Result<PathStat
     let shim_dir = settings.get_bin_dir();
 
     let path_manager = PathManager::new(shim_dir.clone());
+    let shell = InitShell::detect();
 
     Ok(PathStatus {
         shim_dir: shim_dir.display().to_string(),
         is_in_path: path_manager.is_in_path(),
         add_command: path_manager.get_add_to_path_command(),
+        shell,
+        config_file: shell.config_file().map(|p| p.display().to_string()),
+        profile_configured: path_manager.is_configured_in_profile(shell),
     })
 }
 
@@ -181,14 +217,15 @@ pub async fn path_remove(settings: State<'_, SharedSettings>) -> Result<(), Stri
         .map_err(|e| e.to_string())
 }
 
-/// Check if shim directory is in PATH
+/// Check if shim directory is in PATH, or will be once the user's shell
+/// loads its init block
 #[tauri::command]
 pub async fn path_check(settings: State<'_, SharedSettings>) -> Result<bool, String> {
     let settings = settings.read().await;
     let shim_dir = settings.get_bin_dir();
 
     let path_manager = PathManager::new(shim_dir);
-    Ok(path_manager.is_in_path())
+    Ok(path_manager.is_in_path() || path_manager.is_configured_in_profile(InitShell::detect()))
 }
 
 /// Get the command to manually add shim directory to PATH
@@ -200,3 +237,33 @@ pub async fn path_get_add_command(settings: State<'_, SharedSettings>) -> Result
     let path_manager = PathManager::new(shim_dir);
     Ok(path_manager.get_add_to_path_command())
 }
+
+/// Get the init script that puts the shim directory on PATH for a shell
+#[tauri::command]
+pub async fn path_get_init_script(
+    shell_type: String,
+    settings: State<'_, SharedSettings>,
+) -> Result<String, String> {
+    let shell = parse_init_shell(&shell_type)?;
+    let settings = settings.read().await;
+
+    let path_manager = PathManager::new(settings.get_bin_dir());
+    Ok(path_manager.init_script(shell))
+}
+
+/// Generate shell completions for all shims, offering installed versions
+#[tauri::command]
+pub async fn shim_generate_completions(
+    shell_type: String,
+    settings: State<'_, SharedSettings>,
+) -> Result<String, String> {
+    let shell = parse_init_shell(&shell_type)?;
+    let settings = settings.read().await;
+    let base_dir = settings.get_root_dir();
+
+    let manager = ShimManager::new(&base_dir)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(manager.generate_completions(shell).await)
+}
@@ -0,0 +1,461 @@
+use crate::commands::config::SharedSettings;
+use crate::commands::environment::CancellationTokens;
+use crate::commands::terminal::SharedTerminalProfileManager;
+use crate::core::operation_queue::SharedOperationQueue;
+use crate::core::stacks::{
+    self, MachineState, Stack, StackAction, StackApplyResult, StackItemKind, StackItemState,
+    StackPackage, StackPlan, DEFAULT_STACK_PACKAGE_PROVIDERS,
+};
+use crate::core::terminal::{self, template_from_profile};
+use crate::core::{
+    EnvBatchItemState, EnvInstallBatchRequest, EnvironmentManager, SharedProfileManager,
+};
+use crate::platform::env::{self, EnvVarScope, EnvVarTransaction};
+use crate::platform::fs;
+use crate::provider::InstalledFilter;
+use crate::SharedRegistry;
+use serde::Serialize;
+use std::collections::HashSet;
+use tauri::{AppHandle, Emitter, State};
+
+/// One finished stack item, emitted as `stack-apply-progress`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StackApplyProgress {
+    stack_name: String,
+    kind: StackItemKind,
+    key: String,
+    state: StackItemState,
+    message: Option<String>,
+    finished: usize,
+    total: usize,
+}
+
+/// Snapshot of the machine covering only what `stack` references
+async fn capture_machine_state(
+    stack: &Stack,
+    registry: &SharedRegistry,
+    terminal_profiles: &SharedTerminalProfileManager,
+) -> MachineState {
+    let mut machine = MachineState::default();
+
+    let manager = EnvironmentManager::new(registry.clone());
+    for env in &stack.environments {
+        if let Ok(info) = manager
+            .get_environment(&env.env_type, env.provider_id.as_deref())
+            .await
+        {
+            machine.installed_envs.insert(
+                env.env_type.clone(),
+                info.installed_versions
+                    .into_iter()
+                    .map(|v| v.version)
+                    .collect(),
+            );
+            if let Some(current) = info.current_version {
+                machine.current_envs.insert(env.env_type.clone(), current);
+            }
+        }
+    }
+
+    let providers: HashSet<&str> = stack.packages.iter().map(|p| p.provider.as_str()).collect();
+    for provider_id in providers {
+        for package in list_global_packages(registry, provider_id).await {
+            machine.installed_packages.insert(
+                format!("{}:{}", provider_id, package.name),
+                package.version.unwrap_or_default(),
+            );
+        }
+    }
+
+    machine.terminal_profiles = terminal_profiles
+        .read()
+        .await
+        .list_profiles()
+        .iter()
+        .map(|p| p.name.clone())
+        .collect();
+
+    for key in stack.env_vars.keys() {
+        if let Ok(Some(value)) = env::get_persistent_var(key, EnvVarScope::User).await {
+            machine.env_vars.insert(key.clone(), value);
+        }
+    }
+
+    machine
+}
+
+/// Global packages of an available provider; empty when it is missing
+async fn list_global_packages(registry: &SharedRegistry, provider_id: &str) -> Vec<StackPackage> {
+    let Some(provider) = registry.read().await.get(provider_id) else {
+        return Vec::new();
+    };
+    if !provider.is_available().await {
+        return Vec::new();
+    }
+    provider
+        .list_installed(InstalledFilter {
+            global_only: true,
+            ..Default::default()
+        })
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|p| StackPackage {
+            provider: provider_id.to_string(),
+            name: p.name,
+            version: Some(p.version),
+        })
+        .collect()
+}
+
+/// Capture the current global environment versions, the global packages of
+/// `package_providers` (a default set when omitted), an optional terminal
+/// profile and the current values of `env_var_keys` as a new stack
+#[tauri::command]
+pub async fn stack_create_from_current(
+    name: String,
+    description: Option<String>,
+    package_providers: Option<Vec<String>>,
+    terminal_profile_id: Option<String>,
+    env_var_keys: Option<Vec<String>>,
+    registry: State<'_, SharedRegistry>,
+    profiles: State<'_, SharedProfileManager>,
+    terminal_profiles: State<'_, SharedTerminalProfileManager>,
+) -> Result<Stack, String> {
+    if name.trim().is_empty() {
+        return Err("Stack name cannot be empty".into());
+    }
+    let mut stack = Stack::new(name.clone());
+    stack.description = description;
+    stack.environments = profiles.read().await.capture_current_environments().await;
+
+    let package_providers = package_providers.unwrap_or_else(|| {
+        DEFAULT_STACK_PACKAGE_PROVIDERS
+            .iter()
+            .map(|id| id.to_string())
+            .collect()
+    });
+    for provider_id in &package_providers {
+        stack
+            .packages
+            .extend(list_global_packages(registry.inner(), provider_id).await);
+    }
+
+    if let Some(profile_id) = terminal_profile_id {
+        let profile = terminal_profiles
+            .read()
+            .await
+            .get_profile(&profile_id)
+            .cloned()
+            .ok_or_else(|| format!("Terminal profile '{}' not found", profile_id))?;
+        let profile_name = profile.name.clone();
+        stack.terminal_profile = Some(template_from_profile(
+            profile,
+            profile_name,
+            format!("From stack {}", name),
+        ));
+    }
+
+    for key in env_var_keys.unwrap_or_default() {
+        if let Some(value) = env::get_persistent_var(&key, EnvVarScope::User)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            stack.env_vars.insert(key, value);
+        }
+    }
+
+    Ok(stack)
+}
+
+/// Write `stack` to `path` as a shareable JSON file
+#[tauri::command]
+pub async fn stack_export(stack: Stack, path: String) -> Result<(), String> {
+    let json = stack.to_json().map_err(|e| e.to_string())?;
+    fs::write_file_string(&path, &json)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Read and validate a stack file
+#[tauri::command]
+pub async fn stack_import(path: String) -> Result<Stack, String> {
+    let json = fs::read_file_string(&path)
+        .await
+        .map_err(|e| e.to_string())?;
+    Stack::from_json(&json).map_err(|e| e.to_string())
+}
+
+/// What applying `stack` would change on this machine
+#[tauri::command]
+pub async fn stack_preview(
+    stack: Stack,
+    registry: State<'_, SharedRegistry>,
+    terminal_profiles: State<'_, SharedTerminalProfileManager>,
+) -> Result<StackPlan, String> {
+    let machine = capture_machine_state(&stack, registry.inner(), terminal_profiles.inner()).await;
+    Ok(stacks::plan_stack(&stack, &machine))
+}
+
+fn finish_item(
+    app: &AppHandle,
+    result: &mut StackApplyResult,
+    kind: StackItemKind,
+    key: &str,
+    state: StackItemState,
+    message: Option<String>,
+) {
+    if !result.finish(kind, key, state, message.clone()) {
+        return;
+    }
+    let _ = app.emit(
+        "stack-apply-progress",
+        StackApplyProgress {
+            stack_name: result.stack_name.clone(),
+            kind,
+            key: key.to_string(),
+            state,
+            message,
+            finished: result.items.len() - result.count(StackItemState::Pending),
+            total: result.items.len(),
+        },
+    );
+}
+
+/// Apply `stack`: install missing environments through the env install batch
+/// and switch global versions, install packages through batch install, create
+/// the terminal profile and set env vars. A failing item is recorded and the
+/// remaining items still run.
+#[tauri::command]
+pub async fn stack_apply(
+    stack: Stack,
+    registry: State<'_, SharedRegistry>,
+    tokens: State<'_, CancellationTokens>,
+    settings: State<'_, SharedSettings>,
+    jobs: State<'_, SharedOperationQueue>,
+    terminal_profiles: State<'_, SharedTerminalProfileManager>,
+    app: AppHandle,
+) -> Result<StackApplyResult, String> {
+    let machine = capture_machine_state(&stack, registry.inner(), terminal_profiles.inner()).await;
+    let plan = stacks::plan_stack(&stack, &machine);
+    let mut result = StackApplyResult::from_plan(&plan);
+
+    // Environments: batch-install the missing ones, then switch globals
+    let install_requests: Vec<EnvInstallBatchRequest> = stack
+        .environments
+        .iter()
+        .filter(|env| {
+            plan.items_with(StackItemKind::Environment, StackAction::Install)
+                .any(|i| i.key == env.env_type)
+        })
+        .map(|env| EnvInstallBatchRequest {
+            env_type: env.env_type.clone(),
+            version: env.version.clone(),
+            provider_id: env.provider_id.clone(),
+        })
+        .collect();
+    let mut switches: Vec<(String, String)> = plan
+        .items_with(StackItemKind::Environment, StackAction::SwitchGlobal)
+        .filter_map(|i| Some((i.key.clone(), i.target.clone()?)))
+        .collect();
+    if !install_requests.is_empty() {
+        match super::environment::env_install_batch(
+            install_requests.clone(),
+            registry.clone(),
+            tokens.clone(),
+            settings.clone(),
+            jobs.clone(),
+            app.clone(),
+        )
+        .await
+        {
+            Ok(batch) => {
+                for item in batch.items {
+                    if item.state == EnvBatchItemState::Completed {
+                        switches.push((item.request.env_type, item.request.version));
+                    } else {
+                        let message = item.error.unwrap_or_else(|| "Cancelled".into());
+                        finish_item(
+                            &app,
+                            &mut result,
+                            StackItemKind::Environment,
+                            &item.request.env_type,
+                            StackItemState::Failed,
+                            Some(message),
+                        );
+                    }
+                }
+            }
+            Err(e) => {
+                for request in &install_requests {
+                    finish_item(
+                        &app,
+                        &mut result,
+                        StackItemKind::Environment,
+                        &request.env_type,
+                        StackItemState::Failed,
+                        Some(e.clone()),
+                    );
+                }
+            }
+        }
+    }
+    let manager = EnvironmentManager::new(registry.inner().clone());
+    for (env_type, pinned) in switches {
+        let provider_id = stack
+            .environments
+            .iter()
+            .find(|env| env.env_type == env_type)
+            .and_then(|env| env.provider_id.clone());
+        // A freshly installed `20` is switched to as the release it resolved to
+        let installed: Vec<String> = manager
+            .get_environment(&env_type, provider_id.as_deref())
+            .await
+            .map(|info| {
+                info.installed_versions
+                    .into_iter()
+                    .map(|v| v.version)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let version = stacks::best_installed_match(&pinned, &installed)
+            .unwrap_or(&pinned)
+            .to_string();
+        let (state, message) = match manager
+            .set_global_version(&env_type, &version, provider_id.as_deref())
+            .await
+        {
+            Ok(()) => (StackItemState::Completed, None),
+            Err(e) => (
+                StackItemState::Failed,
+                Some(format!("Failed to switch to {}: {}", version, e)),
+            ),
+        };
+        finish_item(
+            &app,
+            &mut result,
+            StackItemKind::Environment,
+            &env_type,
+            state,
+            message,
+        );
+    }
+
+    // Packages: one batch install; failures are reported per package
+    let packages: Vec<&StackPackage> = stack
+        .packages
+        .iter()
+        .filter(|p| {
+            plan.items_with(StackItemKind::Package, StackAction::Install)
+                .any(|i| i.key == p.key())
+        })
+        .collect();
+    if !packages.is_empty() {
+        let specs = packages.iter().map(|p| p.spec()).collect();
+        match super::batch::batch_install(
+            specs,
+            None,
+            None,
+            None,
+            Some(true),
+            app.clone(),
+            registry.clone(),
+            settings.clone(),
+            jobs.clone(),
+        )
+        .await
+        {
+            Ok(batch) => {
+                for package in packages {
+                    let (state, message) = if let Some(failed) =
+                        batch.failed.iter().find(|f| f.name == package.name)
+                    {
+                        (StackItemState::Failed, Some(failed.error.clone()))
+                    } else if let Some(skipped) = batch
+                        .skipped
+                        .iter()
+                        .chain(&batch.pinned)
+                        .find(|s| s.name == package.name)
+                    {
+                        (StackItemState::Skipped, Some(skipped.reason.clone()))
+                    } else {
+                        (StackItemState::Completed, None)
+                    };
+                    finish_item(
+                        &app,
+                        &mut result,
+                        StackItemKind::Package,
+                        &package.key(),
+                        state,
+                        message,
+                    );
+                }
+            }
+            Err(e) => {
+                for package in packages {
+                    finish_item(
+                        &app,
+                        &mut result,
+                        StackItemKind::Package,
+                        &package.key(),
+                        StackItemState::Failed,
+                        Some(e.clone()),
+                    );
+                }
+            }
+        }
+    }
+
+    // Terminal profile
+    if let Some(template) = stack.terminal_profile.as_ref().filter(|_| {
+        plan.items_with(StackItemKind::TerminalProfile, StackAction::Create)
+            .next()
+            .is_some()
+    }) {
+        let shells = terminal::detect_installed_shells()
+            .await
+            .unwrap_or_default();
+        let profile = terminal::profile_from_template(template, &shells);
+        let created = terminal_profiles
+            .write()
+            .await
+            .create_profile(profile)
+            .await;
+        let (state, message) = match created {
+            Ok(_) => (StackItemState::Completed, None),
+            Err(e) => (StackItemState::Failed, Some(e.to_string())),
+        };
+        finish_item(
+            &app,
+            &mut result,
+            StackItemKind::TerminalProfile,
+            &template.name,
+            state,
+            message,
+        );
+    }
+
+    // Env vars: one transaction each so a rejected value does not roll back the rest
+    for item in plan.items_with(StackItemKind::EnvVar, StackAction::Set) {
+        let Some(value) = stack.env_vars.get(&item.key) else {
+            continue;
+        };
+        let mut transaction = EnvVarTransaction::new(EnvVarScope::User);
+        transaction.set(item.key.clone(), value.clone());
+        let (state, message) = match transaction.apply().await {
+            Ok(_) => (StackItemState::Completed, None),
+            Err(e) => (StackItemState::Failed, Some(e.to_string())),
+        };
+        finish_item(
+            &app,
+            &mut result,
+            StackItemKind::EnvVar,
+            &item.key,
+            state,
+            message,
+        );
+    }
+
+    Ok(result)
+}
@@ -1,10 +1,16 @@
 use crate::config::Settings;
+use crate::core::env_disk_usage::EnvUsageLog;
 use crate::core::terminal::{
-    self, PSModuleInfo, PSProfileInfo, PSScriptInfo, ShellConfigEntries, ShellFrameworkInfo,
-    ShellInfo, ShellPlugin, TerminalConfigDiagnostic, TerminalConfigEditorMetadata,
-    TerminalConfigMutationResult, TerminalConfigRestoreResult, TerminalProfile,
-    TerminalProfileManager, TerminalProfileTemplate,
+    self, PSModuleInfo, PSModuleUninstallResult, PSModuleUpdateResult, PSProfileInfo, PSScriptInfo,
+    ShellConfigEntries, ShellConfigEntryKind, ShellFrameworkInfo, ShellInfo, ShellPlugin,
+    TerminalConfigDiagnostic, TerminalConfigEditorMetadata, TerminalConfigMutationResult,
+    TerminalConfigRestoreResult, TerminalProfile, TerminalProfileManager, TerminalProfileTemplate,
 };
+use crate::core::terminal_history::{self, TerminalLaunchRecord};
+use crate::core::terminal_session::{
+    PtySpawnSpec, SharedTerminalSessionManager, TerminalSessionExitEvent, TerminalSessionInfo,
+};
+use crate::core::trash::TrashKind;
 use crate::core::EnvironmentManager;
 use crate::platform::env::{EnvModifications, ShellType};
 use crate::platform::process::ProcessOptions;
@@ -12,7 +18,7 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tauri::State;
+use tauri::{Emitter, State};
 use tokio::sync::RwLock;
 
 use super::launch::LaunchResult;
@@ -259,6 +265,12 @@ async fn build_launch_options(
             .get_env_modifications(env_type, &resolved_version, None)
             .await
             .map_err(|e| e.to_string())?;
+        EnvUsageLog::record(
+            env_type,
+            &resolved_version,
+            profile.cwd.as_deref().map(Path::new),
+        )
+        .await;
         options = apply_env_modifications(options, &env_modifications);
     }
 
@@ -271,23 +283,56 @@ async fn build_launch_options(
     Ok(options)
 }
 
-async fn launch_profile_internal(
+async fn launch_with_shell(
     profile: &TerminalProfile,
+    shell_type: ShellType,
+    executable: &str,
     settings: &Settings,
     registry: crate::SharedRegistry,
 ) -> Result<LaunchResult, String> {
-    let (shell_type, executable) = resolve_profile_shell(profile).await?;
     let args = build_profile_args(profile, shell_type);
     let options = build_launch_options(profile, settings, registry).await?;
     let args_ref: Vec<&str> = args.iter().map(|arg| arg.as_str()).collect();
 
-    let output = crate::platform::process::execute(&executable, &args_ref, Some(options))
+    let output = crate::platform::process::execute(executable, &args_ref, Some(options))
         .await
         .map_err(|e| e.to_string())?;
 
     Ok(output.into())
 }
 
+/// Launch a profile and add the launch to the history. Launches that fail
+/// before the shell is resolved are not recorded.
+async fn launch_profile_internal(
+    profile: &TerminalProfile,
+    settings: &Settings,
+    registry: crate::SharedRegistry,
+) -> Result<LaunchResult, String> {
+    let (shell_type, executable) = resolve_profile_shell(profile).await?;
+    let result = launch_with_shell(profile, shell_type, &executable, settings, registry).await;
+
+    let mut record = TerminalLaunchRecord::new(
+        profile,
+        &executable,
+        settings.terminal.launch_history_record_env_values,
+    );
+    if let Ok(launch) = &result {
+        record.exit_code = Some(launch.exit_code);
+        record.success = launch.success;
+    }
+    if let Err(e) = terminal_history::record_launch(
+        &settings.get_root_dir(),
+        record,
+        settings.terminal.launch_history_max,
+    )
+    .await
+    {
+        warn!("Failed to record terminal launch: {}", e);
+    }
+
+    result
+}
+
 fn legacy_launch_stdout(result: LaunchResult) -> String {
     result.stdout
 }
@@ -413,13 +458,33 @@ pub async fn terminal_update_profile(
     mgr.update_profile(profile).await.map_err(|e| e.to_string())
 }
 
+/// Delete a profile, keeping a copy in the trash
 #[tauri::command]
 pub async fn terminal_delete_profile(
     id: String,
     manager: State<'_, SharedTerminalProfileManager>,
+    settings: State<'_, SharedSettings>,
 ) -> Result<bool, String> {
+    let root_dir = settings.read().await.get_root_dir();
     let mut mgr = manager.write().await;
-    mgr.delete_profile(&id).await.map_err(|e| e.to_string())
+    let Some(profile) = mgr.get_profile(&id).cloned() else {
+        return Ok(false);
+    };
+    let entry = super::trash::trash_before_delete(
+        &root_dir,
+        TrashKind::TerminalProfile,
+        &profile.id,
+        &profile.name,
+        &profile,
+    )
+    .await?;
+    match mgr.delete_profile(&id).await {
+        Ok(deleted) => Ok(deleted),
+        Err(e) => {
+            super::trash::discard(&root_dir, &entry).await;
+            Err(e.to_string())
+        }
+    }
 }
 
 #[tauri::command]
@@ -487,6 +552,67 @@ pub async fn terminal_launch_profile_detailed(
     launch_profile_internal(&profile, &settings_snapshot, registry.inner().clone()).await
 }
 
+/// Recorded profile launches, newest first
+#[tauri::command]
+pub async fn terminal_launch_history(
+    limit: Option<usize>,
+    profile_id: Option<String>,
+    settings: State<'_, SharedSettings>,
+) -> Result<Vec<TerminalLaunchRecord>, String> {
+    let root_dir = settings.read().await.get_root_dir();
+    terminal_history::list_launches(&root_dir, limit, profile_id.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Launch a profile again with the shell overrides and cwd of a history entry.
+/// Recorded env var values are reapplied; names recorded without a value keep
+/// the profile's current value.
+#[tauri::command]
+pub async fn terminal_relaunch(
+    history_id: String,
+    manager: State<'_, SharedTerminalProfileManager>,
+    settings: State<'_, SharedSettings>,
+    registry: State<'_, crate::SharedRegistry>,
+) -> Result<LaunchResult, String> {
+    let settings_snapshot = settings.read().await.clone();
+    let record = terminal_history::get_launch(&settings_snapshot.get_root_dir(), &history_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Launch history entry '{}' not found", history_id))?;
+    let mut profile = {
+        let manager_guard = manager.read().await;
+        manager_guard
+            .get_profile(&record.profile_id)
+            .cloned()
+            .ok_or_else(|| format!("Profile '{}' not found", record.profile_id))?
+    };
+    profile.env_vars.extend(
+        record
+            .env_vars
+            .into_iter()
+            .filter_map(|(key, value)| value.map(|value| (key, value))),
+    );
+    let profile = apply_launch_overrides(profile, record.env_type, record.env_version, record.cwd);
+    launch_profile_internal(&profile, &settings_snapshot, registry.inner().clone()).await
+}
+
+/// Pin a history entry so the size cap never drops it, or unpin it
+#[tauri::command]
+pub async fn terminal_launch_history_pin(
+    history_id: String,
+    pinned: bool,
+    settings: State<'_, SharedSettings>,
+) -> Result<bool, String> {
+    let (root_dir, max_entries) = {
+        let s = settings.read().await;
+        (s.get_root_dir(), s.terminal.launch_history_max)
+    };
+    terminal_history::set_pinned(&root_dir, &history_id, pinned, max_entries)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn terminal_get_proxy_env_vars(
     settings: State<'_, SharedSettings>,
@@ -499,6 +625,151 @@ pub async fn terminal_get_proxy_env_vars(
     Ok(result)
 }
 
+// ============================================================================
+// Interactive PTY Sessions
+// ============================================================================
+
+pub const TERMINAL_SESSION_OUTPUT_EVENT: &str = "terminal-session-output";
+pub const TERMINAL_SESSION_EXIT_EVENT: &str = "terminal-session-exit";
+pub const TERMINAL_PS_MODULE_UPDATE_EVENT: &str = "terminal-ps-module-update-progress";
+
+/// Build the PTY spawn spec for a profile. Env vars and cwd are assembled exactly
+/// like `terminal_launch_profile_detailed`; the startup command is typed into the
+/// session instead of being passed with `-c` so the shell stays interactive.
+async fn build_session_spawn_spec(
+    profile: &TerminalProfile,
+    settings: &Settings,
+    registry: crate::SharedRegistry,
+    cols: u16,
+    rows: u16,
+) -> Result<PtySpawnSpec, String> {
+    let (_, executable) = resolve_profile_shell(profile).await?;
+    let options = build_launch_options(profile, settings, registry).await?;
+    Ok(PtySpawnSpec {
+        program: executable,
+        args: profile.args.clone(),
+        cwd: options.cwd,
+        env: options.env,
+        cols,
+        rows,
+    })
+}
+
+#[tauri::command]
+pub async fn terminal_session_open(
+    profile_id: String,
+    cols: u16,
+    rows: u16,
+    app: tauri::AppHandle,
+    manager: State<'_, SharedTerminalProfileManager>,
+    sessions: State<'_, SharedTerminalSessionManager>,
+    settings: State<'_, SharedSettings>,
+    registry: State<'_, crate::SharedRegistry>,
+) -> Result<TerminalSessionInfo, String> {
+    let profile = {
+        let manager_guard = manager.read().await;
+        manager_guard
+            .get_profile(&profile_id)
+            .cloned()
+            .ok_or_else(|| format!("Profile '{}' not found", profile_id))?
+    };
+    let settings_snapshot = settings.read().await.clone();
+    let spec = build_session_spawn_spec(
+        &profile,
+        &settings_snapshot,
+        registry.inner().clone(),
+        cols,
+        rows,
+    )
+    .await?;
+
+    let output_app = app.clone();
+    let exit_app = app.clone();
+    let info = sessions
+        .open(
+            &profile.id,
+            spec,
+            settings_snapshot.terminal.max_sessions as usize,
+            move |event| {
+                let _ = output_app.emit(TERMINAL_SESSION_OUTPUT_EVENT, event);
+            },
+            move |event| {
+                let _ = exit_app.emit(TERMINAL_SESSION_EXIT_EVENT, event);
+            },
+        )
+        .map_err(|e| e.to_string())?;
+
+    if let Some(cmd) = profile
+        .startup_command
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        let line = if cfg!(windows) {
+            format!("{}\r\n", cmd)
+        } else {
+            format!("{}\n", cmd)
+        };
+        sessions
+            .write(&info.session_id, line.as_bytes())
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(info)
+}
+
+#[tauri::command]
+pub async fn terminal_session_write(
+    session_id: String,
+    data: String,
+    sessions: State<'_, SharedTerminalSessionManager>,
+) -> Result<(), String> {
+    sessions
+        .write(&session_id, data.as_bytes())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn terminal_session_resize(
+    session_id: String,
+    cols: u16,
+    rows: u16,
+    sessions: State<'_, SharedTerminalSessionManager>,
+) -> Result<(), String> {
+    sessions
+        .resize(&session_id, cols, rows)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn terminal_session_close(
+    session_id: String,
+    app: tauri::AppHandle,
+    sessions: State<'_, SharedTerminalSessionManager>,
+) -> Result<(), String> {
+    let exit_code = sessions
+        .close(&session_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let _ = app.emit(
+        TERMINAL_SESSION_EXIT_EVENT,
+        TerminalSessionExitEvent {
+            session_id,
+            exit_code,
+        },
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn terminal_session_list(
+    sessions: State<'_, SharedTerminalSessionManager>,
+) -> Result<Vec<TerminalSessionInfo>, String> {
+    Ok(sessions.list())
+}
+
 // ============================================================================
 // Profile Templates
 // ============================================================================
@@ -607,6 +878,32 @@ pub async fn terminal_append_to_config_verified(
         .map_err(|e| e.to_string())
 }
 
+/// Add or replace an alias/export in place instead of appending a duplicate
+#[tauri::command]
+pub async fn terminal_config_upsert_entry(
+    path: String,
+    shell_type: ShellType,
+    kind: ShellConfigEntryKind,
+    name: String,
+    value: String,
+) -> Result<TerminalConfigMutationResult, String> {
+    terminal::upsert_shell_config_entry(&PathBuf::from(&path), shell_type, kind, &name, &value)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn terminal_config_remove_entry(
+    path: String,
+    shell_type: ShellType,
+    kind: ShellConfigEntryKind,
+    name: String,
+) -> Result<TerminalConfigMutationResult, String> {
+    terminal::remove_shell_config_entry(&PathBuf::from(&path), shell_type, kind, &name)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn terminal_get_config_entries(
     path: String,
@@ -636,6 +933,36 @@ pub fn terminal_validate_config_content(
     ))
 }
 
+async fn find_detected_shell(shell_id: &str) -> Result<ShellInfo, String> {
+    terminal::detect_installed_shells()
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|s| s.id == shell_id)
+        .ok_or_else(|| format!("Shell '{}' not found", shell_id))
+}
+
+#[tauri::command]
+pub async fn shell_profile_analyze(
+    shell_id: String,
+) -> Result<terminal::ShellProfileAnalysis, String> {
+    let shell = find_detected_shell(&shell_id).await?;
+    terminal::analyze_shell_profile(&shell)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn shell_profile_apply_fix(
+    shell_id: String,
+    finding_id: String,
+) -> Result<terminal::ShellProfileFixResult, String> {
+    let shell = find_detected_shell(&shell_id).await?;
+    terminal::apply_shell_profile_fix(&shell, &finding_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn terminal_get_config_editor_metadata(
     path: String,
@@ -820,8 +1147,11 @@ pub async fn terminal_ps_install_module(name: String, scope: String) -> Result<(
 }
 
 #[tauri::command]
-pub async fn terminal_ps_uninstall_module(name: String) -> Result<(), String> {
-    terminal::ps_uninstall_module(&name)
+pub async fn terminal_ps_uninstall_module(
+    name: String,
+    force: Option<bool>,
+) -> Result<PSModuleUninstallResult, String> {
+    terminal::ps_uninstall_module(&name, force.unwrap_or(false))
         .await
         .map_err(|e| e.to_string())
 }
@@ -833,6 +1163,17 @@ pub async fn terminal_ps_update_module(name: String) -> Result<(), String> {
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn terminal_ps_update_all_modules(
+    app: tauri::AppHandle,
+) -> Result<Vec<PSModuleUpdateResult>, String> {
+    terminal::ps_update_all_modules(|progress| {
+        let _ = app.emit(TERMINAL_PS_MODULE_UPDATE_EVENT, progress);
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn terminal_ps_find_module(query: String) -> Result<Vec<PSModuleInfo>, String> {
     terminal::ps_find_module(&query)
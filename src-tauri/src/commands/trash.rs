@@ -0,0 +1,135 @@
+use crate::commands::config::SharedSettings;
+use crate::commands::custom_detection::SharedCustomDetectionManager;
+use crate::commands::terminal::SharedTerminalProfileManager;
+use crate::core::custom_detection::CustomDetectionRule;
+use crate::core::terminal::TerminalProfile;
+use crate::core::trash::{self, TrashEntry, TrashKind, TrashRestoreResult};
+use crate::core::{EnvironmentProfile, SharedProfileManager};
+use crate::error::CogniaResult;
+use serde::Serialize;
+use std::path::Path;
+use tauri::State;
+
+/// Move an object into the trash before its manager deletes it. Returns
+/// the entry so a failed deletion can take it back out with [`discard`].
+pub(crate) async fn trash_before_delete<T: Serialize>(
+    root_dir: &Path,
+    kind: TrashKind,
+    object_id: &str,
+    label: &str,
+    object: &T,
+) -> Result<TrashEntry, String> {
+    let entry = TrashEntry::new(kind, object_id, label, object, chrono::Utc::now())
+        .map_err(|e| e.to_string())?;
+    trash::put(root_dir, entry).await.map_err(|e| e.to_string())
+}
+
+/// Forget a trash entry whose deletion did not go through
+pub(crate) async fn discard(root_dir: &Path, entry: &TrashEntry) {
+    if let Err(e) = trash::remove_entry(root_dir, &entry.id).await {
+        log::warn!("Failed to drop trash entry {}: {}", entry.id, e);
+    }
+}
+
+/// Trashed objects, most recently deleted first
+#[tauri::command]
+pub async fn trash_list(settings: State<'_, SharedSettings>) -> Result<Vec<TrashEntry>, String> {
+    let root_dir = settings.read().await.get_root_dir();
+    trash::list_entries(&root_dir)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Put a trashed object back. If its ID was taken in the meantime it comes
+/// back under `<id>-restored` (or `-restored-2`, ...), except for shims,
+/// which are named after their command and are refused instead. The entry
+/// is taken out of the trash first, so it cannot be restored twice.
+#[tauri::command]
+pub async fn trash_restore(
+    id: String,
+    settings: State<'_, SharedSettings>,
+    terminal_profiles: State<'_, SharedTerminalProfileManager>,
+    custom_rules: State<'_, SharedCustomDetectionManager>,
+    profiles: State<'_, SharedProfileManager>,
+) -> Result<TrashRestoreResult, String> {
+    let root_dir = settings.read().await.get_root_dir();
+    let entry = trash::take_entry(&root_dir, &id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let restored = restore_object(
+        &root_dir,
+        &entry,
+        &terminal_profiles,
+        &custom_rules,
+        &profiles,
+    )
+    .await;
+    let restored_id = match restored {
+        Ok(restored_id) => restored_id,
+        Err(e) => {
+            if let Err(put_err) = trash::put(&root_dir, entry).await {
+                log::warn!("Failed to return entry {} to the trash: {}", id, put_err);
+            }
+            return Err(e.to_string());
+        }
+    };
+    Ok(TrashRestoreResult {
+        renamed: restored_id != entry.object_id,
+        entry_id: entry.id,
+        kind: entry.kind,
+        restored_id,
+    })
+}
+
+/// Hand a trashed object back to its manager. Returns the ID it was
+/// restored under.
+async fn restore_object(
+    root_dir: &Path,
+    entry: &TrashEntry,
+    terminal_profiles: &SharedTerminalProfileManager,
+    custom_rules: &SharedCustomDetectionManager,
+    profiles: &SharedProfileManager,
+) -> CogniaResult<String> {
+    match entry.kind {
+        TrashKind::TerminalProfile => {
+            let mut profile: TerminalProfile = entry.object()?;
+            let mut mgr = terminal_profiles.write().await;
+            profile.id = trash::restore_id(&profile.id, |candidate| {
+                mgr.get_profile(candidate).is_some()
+            });
+            mgr.restore_profile(profile).await
+        }
+        TrashKind::CustomRule => {
+            let mut rule: CustomDetectionRule = entry.object()?;
+            let mut mgr = custom_rules.write().await;
+            rule.id = trash::restore_id(&rule.id, |candidate| mgr.get_rule(candidate).is_some());
+            let rule_id = rule.id.clone();
+            mgr.restore_rule(rule)?;
+            mgr.save().await?;
+            Ok(rule_id)
+        }
+        TrashKind::Shim => trash::restore_shim(root_dir, entry).await,
+        TrashKind::EnvironmentProfile => {
+            let mut profile: EnvironmentProfile = entry.object()?;
+            let mut mgr = profiles.write().await;
+            profile.id = trash::restore_id(&profile.id, |candidate| mgr.get(candidate).is_some());
+            Ok(mgr.create(profile).await?.id)
+        }
+    }
+}
+
+/// Permanently delete trash entries older than `older_than_days`, or all of
+/// them when it is omitted. Returns the number of entries purged.
+#[tauri::command]
+pub async fn trash_purge(
+    older_than_days: Option<u32>,
+    settings: State<'_, SharedSettings>,
+) -> Result<usize, String> {
+    let root_dir = settings.read().await.get_root_dir();
+    let cutoff =
+        older_than_days.map(|days| chrono::Utc::now() - chrono::Duration::days(days as i64));
+    trash::purge(&root_dir, cutoff)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,393 @@
+use crate::commands::environment::SharedEolCache;
+use crate::commands::notifications::notify;
+use crate::commands::updater::{self_check_update, SelfUpdateInfo};
+use crate::core::notifications::{
+    Notification, NotificationCategory, NotificationCenter, NotificationSeverity,
+    SharedNotificationCenter,
+};
+use crate::core::{EnvUpdateCheckResult, EnvironmentManager};
+use crate::platform::scheduler::{self, ScheduleInterval, ScheduleStatus};
+use crate::SharedRegistry;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+const BACKGROUND_REPORT_FILE: &str = "background-update-check.json";
+
+/// Result of a headless scheduled check, read by the UI on next start
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackgroundUpdateReport {
+    pub checked_at: String,
+    pub environments: Vec<EnvUpdateCheckResult>,
+    pub outdated_environments: usize,
+    pub self_update: Option<SelfUpdateInfo>,
+    /// Current versions that are past or near end-of-life
+    #[serde(default)]
+    pub eol_warnings: Vec<BackgroundEolWarning>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackgroundEolWarning {
+    pub env_type: String,
+    pub version: String,
+    pub cycle: String,
+    pub eol: Option<String>,
+    /// `false` when end-of-life is approaching but not yet reached
+    pub is_eol: bool,
+}
+
+impl BackgroundUpdateReport {
+    pub fn has_updates(&self) -> bool {
+        self.outdated_environments > 0
+            || self
+                .self_update
+                .as_ref()
+                .is_some_and(|info| info.update_available)
+    }
+
+    fn notification_body(&self) -> String {
+        let mut parts = Vec::new();
+        if self.outdated_environments > 0 {
+            let names: Vec<&str> = self
+                .environments
+                .iter()
+                .filter(|env| env.is_outdated)
+                .map(|env| env.env_type.as_str())
+                .collect();
+            parts.push(format!(
+                "{} environment update(s): {}",
+                self.outdated_environments,
+                names.join(", ")
+            ));
+        }
+        if let Some(version) = self
+            .self_update
+            .as_ref()
+            .filter(|info| info.update_available)
+            .and_then(|info| info.latest_version.as_deref())
+        {
+            parts.push(format!("CogniaLauncher {} is available", version));
+        }
+        parts.join("\n")
+    }
+
+    fn eol_notification_body(&self) -> String {
+        self.eol_warnings
+            .iter()
+            .map(|warning| {
+                let when = warning.eol.as_deref().unwrap_or("unknown date");
+                if warning.is_eol {
+                    format!(
+                        "{} {} reached end-of-life on {}",
+                        warning.env_type, warning.version, when
+                    )
+                } else {
+                    format!(
+                        "{} {} reaches end-of-life on {}",
+                        warning.env_type, warning.version, when
+                    )
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn report_path() -> Option<PathBuf> {
+    crate::platform::fs::get_config_dir().map(|dir| dir.join(BACKGROUND_REPORT_FILE))
+}
+
+fn current_exe() -> Result<PathBuf, String> {
+    std::env::current_exe().map_err(|e| format!("Cannot resolve executable path: {}", e))
+}
+
+/// Register an OS scheduled task that runs a headless update check
+#[tauri::command]
+pub async fn schedule_update_check_enable(interval: String) -> Result<ScheduleStatus, String> {
+    let interval = ScheduleInterval::parse(&interval)
+        .ok_or_else(|| format!("Unsupported interval '{}' (use daily or weekly)", interval))?;
+    let exe = current_exe()?;
+    scheduler::register(&exe, interval)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(scheduler::status(Some(&exe)).await)
+}
+
+/// Remove the scheduled task. Returns whether one had been registered.
+#[tauri::command]
+pub async fn schedule_update_check_disable() -> Result<bool, String> {
+    scheduler::unregister().await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn schedule_update_check_status() -> Result<ScheduleStatus, String> {
+    let exe = std::env::current_exe().ok();
+    Ok(scheduler::status(exe.as_deref()).await)
+}
+
+/// Last report written by a scheduled check, if any
+#[tauri::command]
+pub async fn schedule_update_check_last_report() -> Result<Option<BackgroundUpdateReport>, String> {
+    let Some(path) = report_path() else {
+        return Ok(None);
+    };
+    match tokio::fs::read_to_string(&path).await {
+        Ok(content) => serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| format!("Invalid background update report: {}", e)),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Re-register the scheduled task if the binary moved (e.g. after self-update)
+pub async fn refresh_schedule_after_update() {
+    let Ok(exe) = std::env::current_exe() else {
+        return;
+    };
+    match scheduler::refresh_if_moved(&exe).await {
+        Ok(Some(record)) => log::info!(
+            "Re-registered scheduled update check for {}",
+            record.executable
+        ),
+        Ok(None) => {}
+        Err(e) => log::warn!("Failed to re-register scheduled update check: {}", e),
+    }
+}
+
+async fn collect_background_report(app: &AppHandle) -> BackgroundUpdateReport {
+    let mut errors = Vec::new();
+
+    let registry = app.state::<SharedRegistry>().inner().clone();
+    let environments = match EnvironmentManager::new(registry)
+        .check_all_env_updates()
+        .await
+    {
+        Ok(results) => results,
+        Err(e) => {
+            errors.push(format!("environment update check failed: {}", e));
+            Vec::new()
+        }
+    };
+
+    let self_update = match self_check_update(app.clone()).await {
+        Ok(info) => {
+            if let Some(message) = &info.error_message {
+                errors.push(format!("self update check failed: {}", message));
+            }
+            Some(info)
+        }
+        Err(e) => {
+            errors.push(format!("self update check failed: {}", e));
+            None
+        }
+    };
+
+    let eol_cache = app.state::<SharedEolCache>().inner().clone();
+    let mut eol_warnings = Vec::new();
+    for env in &environments {
+        let Some(version) = env.current_version.as_deref() else {
+            continue;
+        };
+        match eol_cache.get_version_eol(&env.env_type, version).await {
+            Ok(Some(cycle)) if cycle.is_eol || cycle.eol_approaching => {
+                eol_warnings.push(BackgroundEolWarning {
+                    env_type: env.env_type.clone(),
+                    version: version.to_string(),
+                    cycle: cycle.cycle,
+                    eol: cycle.eol,
+                    is_eol: cycle.is_eol,
+                });
+            }
+            Ok(_) => {}
+            Err(e) => log::debug!("EOL check for {} failed: {}", env.env_type, e),
+        }
+    }
+
+    BackgroundUpdateReport {
+        checked_at: chrono::Utc::now().to_rfc3339(),
+        outdated_environments: environments.iter().filter(|env| env.is_outdated).count(),
+        environments,
+        self_update,
+        eol_warnings,
+        errors,
+    }
+}
+
+/// Entry point for `--background-update-check`: check, persist the report,
+/// notify when something is outdated and return the process exit code.
+pub fn run_background_update_check(app: &AppHandle) -> i32 {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.hide();
+    }
+
+    let report = tauri::async_runtime::block_on(async {
+        refresh_schedule_after_update().await;
+        if let Some(db_path) = NotificationCenter::default_db_path() {
+            let center = app.state::<SharedNotificationCenter>();
+            if let Err(e) = center.attach_store(&db_path).await {
+                log::warn!("Notification history unavailable: {}", e);
+            }
+        }
+        collect_background_report(app).await
+    });
+
+    let mut exit_code = 0;
+    match (
+        report_path(),
+        serde_json::to_string_pretty(&report).map_err(|e| e.to_string()),
+    ) {
+        (Some(path), Ok(content)) => {
+            if let Some(parent) = path.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            if let Err(e) = std::fs::write(&path, content) {
+                log::warn!("Failed to write background update report: {}", e);
+                exit_code = 1;
+            }
+        }
+        (None, _) => {
+            log::warn!("Cannot resolve config directory for background update report");
+            exit_code = 1;
+        }
+        (_, Err(e)) => {
+            log::warn!("Failed to serialize background update report: {}", e);
+            exit_code = 1;
+        }
+    }
+
+    tauri::async_runtime::block_on(async {
+        if report.has_updates() {
+            let payload = serde_json::json!({
+                "outdatedEnvironments": report.outdated_environments,
+                "selfUpdate": report.self_update,
+            });
+            notify(
+                app,
+                Notification::new(
+                    NotificationCategory::Update,
+                    NotificationSeverity::Info,
+                    "Updates available",
+                    report.notification_body(),
+                )
+                .with_payload(payload),
+            )
+            .await;
+        }
+        if !report.eol_warnings.is_empty() {
+            let severity = if report.eol_warnings.iter().any(|warning| warning.is_eol) {
+                NotificationSeverity::Error
+            } else {
+                NotificationSeverity::Warning
+            };
+            notify(
+                app,
+                Notification::new(
+                    NotificationCategory::Eol,
+                    severity,
+                    "End-of-life versions in use",
+                    report.eol_notification_body(),
+                )
+                .with_payload(serde_json::json!({ "warnings": report.eol_warnings })),
+            )
+            .await;
+        }
+    });
+
+    log::info!(
+        "Background update check finished: {} outdated environment(s), {} error(s)",
+        report.outdated_environments,
+        report.errors.len()
+    );
+    exit_code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env_result(env_type: &str, outdated: bool) -> EnvUpdateCheckResult {
+        EnvUpdateCheckResult {
+            env_type: env_type.to_string(),
+            provider_id: env_type.to_string(),
+            current_version: Some("1.0.0".to_string()),
+            latest_version: Some("2.0.0".to_string()),
+            latest_lts: None,
+            newer_count: usize::from(outdated),
+            is_outdated: outdated,
+        }
+    }
+
+    #[test]
+    fn test_report_notification_body() {
+        let report = BackgroundUpdateReport {
+            checked_at: "2026-01-01T00:00:00Z".to_string(),
+            environments: vec![env_result("node", true), env_result("python", false)],
+            outdated_environments: 1,
+            self_update: None,
+            eol_warnings: vec![],
+            errors: vec![],
+        };
+        assert!(report.has_updates());
+        assert_eq!(report.notification_body(), "1 environment update(s): node");
+    }
+
+    #[test]
+    fn test_report_without_updates() {
+        let report = BackgroundUpdateReport {
+            checked_at: "2026-01-01T00:00:00Z".to_string(),
+            environments: vec![env_result("python", false)],
+            outdated_environments: 0,
+            self_update: None,
+            eol_warnings: vec![],
+            errors: vec!["self update check failed: offline".to_string()],
+        };
+        assert!(!report.has_updates());
+
+        let json = serde_json::to_string(&report).unwrap();
+        let parsed: BackgroundUpdateReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.errors.len(), 1);
+        assert_eq!(parsed.environments[0].env_type, "python");
+    }
+
+    #[test]
+    fn test_report_eol_notification_body() {
+        let report = BackgroundUpdateReport {
+            checked_at: "2026-01-01T00:00:00Z".to_string(),
+            environments: vec![env_result("python", false)],
+            outdated_environments: 0,
+            self_update: None,
+            eol_warnings: vec![
+                BackgroundEolWarning {
+                    env_type: "python".to_string(),
+                    version: "3.8.18".to_string(),
+                    cycle: "3.8".to_string(),
+                    eol: Some("2024-10-07".to_string()),
+                    is_eol: true,
+                },
+                BackgroundEolWarning {
+                    env_type: "node".to_string(),
+                    version: "20.11.0".to_string(),
+                    cycle: "20".to_string(),
+                    eol: Some("2026-04-30".to_string()),
+                    is_eol: false,
+                },
+            ],
+            errors: vec![],
+        };
+        assert_eq!(
+            report.eol_notification_body(),
+            "python 3.8.18 reached end-of-life on 2024-10-07\n\
+             node 20.11.0 reaches end-of-life on 2026-04-30"
+        );
+
+        // Reports written before EOL checks existed still parse
+        let parsed: BackgroundUpdateReport = serde_json::from_str(
+            r#"{"checkedAt":"x","environments":[],"outdatedEnvironments":0,"selfUpdate":null,"errors":[]}"#,
+        )
+        .unwrap();
+        assert!(parsed.eol_warnings.is_empty());
+    }
+}
@@ -1,21 +1,33 @@
-use crate::config::{UpdateSettings, UpdateSourceMode};
+use crate::config::{UpdateChannel, UpdateSettings, UpdateSourceMode};
 use crate::SharedSettings;
 use reqwest::Url;
-use serde::Serialize;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use tauri::{AppHandle, Emitter, Manager};
-use tauri_plugin_updater::{Error as UpdaterError, Update, Updater, UpdaterExt};
+use tauri_plugin_updater::{
+    Error as UpdaterError, RemoteRelease, Update, Updater, UpdaterBuilder, UpdaterExt,
+};
 
 const EMBEDDED_OFFICIAL_ENDPOINTS: &[&str] =
     &["https://github.com/ElementAstro/CogniaLauncher/releases/latest/download/latest.json"];
-const EMBEDDED_MIRROR_ENDPOINTS: &[&str] = &[
-    "https://gh-proxy.com/https://github.com/ElementAstro/CogniaLauncher/releases/latest/download/latest.json",
-];
+const EMBEDDED_BETA_ENDPOINTS: &[&str] =
+    &["https://github.com/ElementAstro/CogniaLauncher/releases/download/beta/latest.json"];
+const EMBEDDED_NIGHTLY_ENDPOINTS: &[&str] =
+    &["https://github.com/ElementAstro/CogniaLauncher/releases/download/nightly/latest.json"];
+const MIRROR_PROXY_PREFIX: &str = "https://gh-proxy.com/";
 
-#[derive(Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SelfUpdateInfo {
     pub current_version: String,
     pub latest_version: Option<String>,
     pub update_available: bool,
+    /// The channel's latest build is older than the running one (e.g. after
+    /// moving from beta back to stable); installing it needs confirmation
+    #[serde(default)]
+    pub downgrade_available: bool,
+    #[serde(default)]
+    pub channel: String,
     pub release_notes: Option<String>,
     pub selected_source: Option<String>,
     pub attempted_sources: Vec<String>,
@@ -23,6 +35,22 @@ pub struct SelfUpdateInfo {
     pub error_message: Option<String>,
 }
 
+/// What switching to a channel would install
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SelfChannelVersion {
+    pub channel: String,
+    pub current_version: String,
+    pub version: Option<String>,
+    pub release_notes: Option<String>,
+    /// The running build already is the channel's latest
+    pub is_current: bool,
+    /// Installing this build would move to an older version
+    pub is_downgrade: bool,
+    pub selected_source: Option<String>,
+    pub error_category: Option<String>,
+    pub error_message: Option<String>,
+}
+
 #[derive(Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SelfUpdateProgressEvent {
@@ -63,6 +91,7 @@ enum UpdateErrorCategory {
     Validation,
     Signature,
     NoUpdate,
+    DowngradeRequiresConfirmation,
     Unknown,
 }
 
@@ -75,6 +104,7 @@ fn as_str(self) -> &'static str {
             Self::Validation => "validation",
             Self::Signature => "signature",
             Self::NoUpdate => "no_update",
+            Self::DowngradeRequiresConfirmation => "downgrade_requires_confirmation",
             Self::Unknown => "unknown",
         }
     }
@@ -93,17 +123,31 @@ struct UpdateSourceCandidate {
 }
 
 impl UpdateSourceCandidate {
-    fn official() -> Self {
+    /// Stable keeps the bundled updater config; other channels have their own feed.
+    fn official(channel: UpdateChannel) -> Self {
+        let strategy = match channel {
+            UpdateChannel::Stable => EndpointStrategy::Default,
+            _ => EndpointStrategy::Override(parse_static_endpoints(official_channel_endpoints(
+                channel,
+            ))),
+        };
         Self {
             source: UpdateSourceKind::Official,
-            strategy: EndpointStrategy::Default,
+            strategy,
         }
     }
 
-    fn mirror() -> Self {
+    fn mirror(channel: UpdateChannel) -> Self {
+        let endpoints = official_channel_endpoints(channel)
+            .iter()
+            .map(|endpoint| format!("{}{}", MIRROR_PROXY_PREFIX, endpoint))
+            .map(|endpoint| {
+                Url::parse(&endpoint).expect("mirror updater endpoint must be valid URL")
+            })
+            .collect();
         Self {
             source: UpdateSourceKind::Mirror,
-            strategy: EndpointStrategy::Override(parse_static_endpoints(EMBEDDED_MIRROR_ENDPOINTS)),
+            strategy: EndpointStrategy::Override(endpoints),
         }
     }
 
@@ -132,6 +176,14 @@ fn new(source: UpdateSourceKind, category: UpdateErrorCategory, message: String)
     }
 }
 
+fn official_channel_endpoints(channel: UpdateChannel) -> &'static [&'static str] {
+    match channel {
+        UpdateChannel::Stable => EMBEDDED_OFFICIAL_ENDPOINTS,
+        UpdateChannel::Beta => EMBEDDED_BETA_ENDPOINTS,
+        UpdateChannel::Nightly => EMBEDDED_NIGHTLY_ENDPOINTS,
+    }
+}
+
 fn parse_static_endpoints(values: &[&str]) -> Vec<Url> {
     values
         .iter()
@@ -139,10 +191,11 @@ fn parse_static_endpoints(values: &[&str]) -> Vec<Url> {
         .collect()
 }
 
-fn parse_custom_endpoints(endpoints: &[String]) -> Vec<Url> {
+fn parse_custom_endpoints(endpoints: &[String], channel: UpdateChannel) -> Vec<Url> {
     endpoints
         .iter()
-        .filter_map(|value| match Url::parse(value) {
+        .map(|value| value.replace("{{channel}}", channel.as_str()))
+        .filter_map(|value| match Url::parse(&value) {
             Ok(url) => Some(url),
             Err(error) => {
                 log::warn!(
@@ -158,30 +211,129 @@ fn parse_custom_endpoints(endpoints: &[String]) -> Vec<Url> {
 
 fn resolve_update_source_candidates(
     update_settings: &UpdateSettings,
+    channel: UpdateChannel,
 ) -> Vec<UpdateSourceCandidate> {
     let mut candidates = Vec::new();
 
     match update_settings.source_mode {
-        UpdateSourceMode::Official => candidates.push(UpdateSourceCandidate::official()),
-        UpdateSourceMode::Mirror => candidates.push(UpdateSourceCandidate::mirror()),
+        UpdateSourceMode::Official => candidates.push(UpdateSourceCandidate::official(channel)),
+        UpdateSourceMode::Mirror => candidates.push(UpdateSourceCandidate::mirror(channel)),
         UpdateSourceMode::Custom => candidates.push(UpdateSourceCandidate::custom(
-            parse_custom_endpoints(&update_settings.custom_endpoints),
+            parse_custom_endpoints(&update_settings.custom_endpoints, channel),
         )),
     }
 
     if update_settings.fallback_to_official
         && !matches!(update_settings.source_mode, UpdateSourceMode::Official)
     {
-        candidates.push(UpdateSourceCandidate::official());
+        candidates.push(UpdateSourceCandidate::official(channel));
     }
 
     if candidates.is_empty() {
-        candidates.push(UpdateSourceCandidate::official());
+        candidates.push(UpdateSourceCandidate::official(channel));
     }
 
     candidates
 }
 
+/// Release track of a build, derived from its pre-release tag.
+///
+/// The derived order is the rank between builds sharing the same
+/// `major.minor.patch`: a nightly is cut from the tree after that version
+/// shipped, so `1.4.0-nightly.20240601` sorts *above* `1.4.0` and moving a
+/// stable user onto nightly is not a downgrade. Betas precede their release.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum ReleaseTrack {
+    Beta,
+    Stable,
+    Nightly,
+}
+
+impl ReleaseTrack {
+    fn of(version: &Version) -> Self {
+        if version.pre.is_empty() {
+            Self::Stable
+        } else if version.pre.as_str().starts_with("nightly") {
+            Self::Nightly
+        } else {
+            Self::Beta
+        }
+    }
+
+    fn allowed_on(self, channel: UpdateChannel) -> bool {
+        match channel {
+            UpdateChannel::Stable => self == Self::Stable,
+            UpdateChannel::Beta => self != Self::Nightly,
+            UpdateChannel::Nightly => true,
+        }
+    }
+}
+
+fn compare_release_versions(a: &Version, b: &Version) -> Ordering {
+    (a.major, a.minor, a.patch)
+        .cmp(&(b.major, b.minor, b.patch))
+        .then_with(|| ReleaseTrack::of(a).cmp(&ReleaseTrack::of(b)))
+        .then_with(|| a.cmp_precedence(b))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReleaseDirection {
+    Upgrade,
+    Downgrade,
+    None,
+}
+
+/// Whether `candidate` from `channel`'s feed should replace `current`.
+/// An older build is only offered when the running build does not belong to
+/// the channel anymore, i.e. the user switched to a more conservative channel.
+fn classify_release(
+    current: &Version,
+    candidate: &Version,
+    channel: UpdateChannel,
+) -> ReleaseDirection {
+    if !ReleaseTrack::of(candidate).allowed_on(channel) {
+        return ReleaseDirection::None;
+    }
+    match compare_release_versions(candidate, current) {
+        Ordering::Greater => ReleaseDirection::Upgrade,
+        Ordering::Less if !ReleaseTrack::of(current).allowed_on(channel) => {
+            ReleaseDirection::Downgrade
+        }
+        _ => ReleaseDirection::None,
+    }
+}
+
+fn classify_release_strings(
+    current: &str,
+    candidate: &str,
+    channel: UpdateChannel,
+) -> ReleaseDirection {
+    match (Version::parse(current), Version::parse(candidate)) {
+        (Ok(current), Ok(candidate)) => classify_release(&current, &candidate, channel),
+        _ => ReleaseDirection::None,
+    }
+}
+
+/// Which releases the updater reports from a feed.
+#[derive(Debug, Clone, Copy)]
+enum ReleaseSelection {
+    /// Newer builds, plus older ones after a channel switch
+    Changes(UpdateChannel),
+    /// The channel's latest build regardless of the running version
+    Latest(UpdateChannel),
+}
+
+impl ReleaseSelection {
+    fn accepts(self, current: &Version, release: &RemoteRelease) -> bool {
+        match self {
+            Self::Changes(channel) => {
+                classify_release(current, &release.version, channel) != ReleaseDirection::None
+            }
+            Self::Latest(channel) => ReleaseTrack::of(&release.version).allowed_on(channel),
+        }
+    }
+}
+
 fn push_attempted_source(attempted: &mut Vec<UpdateSourceKind>, source: UpdateSourceKind) {
     if !attempted.contains(&source) {
         attempted.push(source);
@@ -242,26 +394,35 @@ fn categorize_updater_error(error: &UpdaterError) -> UpdateErrorCategory {
     }
 }
 
+fn updater_builder(app: &AppHandle, selection: ReleaseSelection) -> UpdaterBuilder {
+    app.updater_builder()
+        .version_comparator(move |current, release| selection.accepts(&current, &release))
+}
+
 fn build_updater_with_endpoints(
     app: &AppHandle,
+    selection: ReleaseSelection,
     endpoints: Vec<Url>,
 ) -> Result<Updater, UpdaterError> {
-    app.updater_builder().endpoints(endpoints)?.build()
+    updater_builder(app, selection)
+        .endpoints(endpoints)?
+        .build()
 }
 
 fn build_updater_for_candidate(
     app: &AppHandle,
     candidate: &UpdateSourceCandidate,
+    selection: ReleaseSelection,
 ) -> Result<Updater, UpdateSourceFailure> {
     let build_result = match &candidate.strategy {
         EndpointStrategy::Override(endpoints) => {
-            build_updater_with_endpoints(app, endpoints.clone())
+            build_updater_with_endpoints(app, selection, endpoints.clone())
         }
-        EndpointStrategy::Default => match app.updater_builder().build() {
+        EndpointStrategy::Default => match updater_builder(app, selection).build() {
             Ok(updater) => Ok(updater),
             Err(UpdaterError::EmptyEndpoints) => {
                 let embedded = parse_static_endpoints(EMBEDDED_OFFICIAL_ENDPOINTS);
-                build_updater_with_endpoints(app, embedded)
+                build_updater_with_endpoints(app, selection, embedded)
             }
             Err(error) => Err(error),
         },
@@ -279,8 +440,9 @@ fn build_updater_for_candidate(
 async fn check_candidate_for_update(
     app: &AppHandle,
     candidate: &UpdateSourceCandidate,
+    selection: ReleaseSelection,
 ) -> Result<Option<Update>, UpdateSourceFailure> {
-    let updater = build_updater_for_candidate(app, candidate)?;
+    let updater = build_updater_for_candidate(app, candidate, selection)?;
     updater.check().await.map_err(|error| {
         UpdateSourceFailure::new(
             candidate.source,
@@ -290,10 +452,12 @@ async fn check_candidate_for_update(
     })
 }
 
+#[allow(clippy::too_many_arguments)]
 fn build_self_update_info(
     current_version: &str,
+    channel: UpdateChannel,
     latest_version: Option<String>,
-    update_available: bool,
+    direction: ReleaseDirection,
     release_notes: Option<String>,
     selected_source: Option<UpdateSourceKind>,
     attempted_sources: &[UpdateSourceKind],
@@ -302,7 +466,9 @@ fn build_self_update_info(
     SelfUpdateInfo {
         current_version: current_version.to_string(),
         latest_version,
-        update_available,
+        update_available: direction == ReleaseDirection::Upgrade,
+        downgrade_available: direction == ReleaseDirection::Downgrade,
+        channel: channel.as_str().to_string(),
         release_notes,
         selected_source: selected_source.map(|source| source.as_str().to_string()),
         attempted_sources: attempted_source_labels(attempted_sources),
@@ -376,43 +542,56 @@ async fn load_update_settings(app: &AppHandle) -> UpdateSettings {
     UpdateSettings::default()
 }
 
-#[tauri::command]
-pub async fn self_check_update(app: AppHandle) -> Result<SelfUpdateInfo, String> {
-    let current_version = app
-        .config()
+fn current_app_version(app: &AppHandle) -> String {
+    app.config()
         .version
         .clone()
-        .unwrap_or_else(|| "0.1.0".to_string());
+        .unwrap_or_else(|| "0.1.0".to_string())
+}
 
-    let update_settings = load_update_settings(&app).await;
-    let candidates = resolve_update_source_candidates(&update_settings);
+#[tauri::command]
+pub async fn self_check_update(app: AppHandle) -> Result<SelfUpdateInfo, String> {
+    let info = check_channel_update(&app).await;
+    if let Err(error) = crate::tray::set_has_update(&app, info.update_available).await {
+        log::debug!("Failed to sync tray update state: {}", error);
+    }
+    Ok(info)
+}
+
+async fn check_channel_update(app: &AppHandle) -> SelfUpdateInfo {
+    let current_version = current_app_version(app);
+    let update_settings = load_update_settings(app).await;
+    let channel = update_settings.update_channel;
+    let candidates = resolve_update_source_candidates(&update_settings, channel);
     let mut attempted_sources: Vec<UpdateSourceKind> = Vec::new();
     let mut failures: Vec<UpdateSourceFailure> = Vec::new();
 
     for candidate in &candidates {
         push_attempted_source(&mut attempted_sources, candidate.source);
-        match check_candidate_for_update(&app, candidate).await {
+        match check_candidate_for_update(app, candidate, ReleaseSelection::Changes(channel)).await {
             Ok(Some(update)) => {
-                return Ok(build_self_update_info(
+                return build_self_update_info(
                     &current_version,
+                    channel,
                     Some(update.version.clone()),
-                    true,
+                    classify_release_strings(&current_version, &update.version, channel),
                     update.body.clone(),
                     Some(candidate.source),
                     &attempted_sources,
                     None,
-                ));
+                );
             }
             Ok(None) => {
-                return Ok(build_self_update_info(
+                return build_self_update_info(
                     &current_version,
+                    channel,
                     Some(current_version.clone()),
-                    false,
+                    ReleaseDirection::None,
                     None,
                     Some(candidate.source),
                     &attempted_sources,
                     None,
-                ));
+                );
             }
             Err(failure) => {
                 log::warn!(
@@ -432,28 +611,108 @@ pub async fn self_check_update(app: AppHandle) -> Result<SelfUpdateInfo, String>
             "Unknown self-update check failure".to_string(),
         )
     });
-    Ok(build_self_update_info(
+    build_self_update_info(
         &current_version,
+        channel,
         None,
-        false,
+        ReleaseDirection::None,
         None,
         None,
         &attempted_sources,
         Some(&fallback_failure),
-    ))
+    )
+}
+
+/// Report the latest build of each channel (or only `channel`) so the UI can
+/// show what switching channels would install.
+#[tauri::command]
+pub async fn self_list_available_versions(
+    app: AppHandle,
+    channel: Option<UpdateChannel>,
+) -> Result<Vec<SelfChannelVersion>, String> {
+    let current_version = current_app_version(&app);
+    let update_settings = load_update_settings(&app).await;
+    let channels = match channel {
+        Some(channel) => vec![channel],
+        None => vec![
+            UpdateChannel::Stable,
+            UpdateChannel::Beta,
+            UpdateChannel::Nightly,
+        ],
+    };
+
+    let mut versions = Vec::with_capacity(channels.len());
+    for channel in channels {
+        let mut entry = SelfChannelVersion {
+            channel: channel.as_str().to_string(),
+            current_version: current_version.clone(),
+            version: None,
+            release_notes: None,
+            is_current: false,
+            is_downgrade: false,
+            selected_source: None,
+            error_category: None,
+            error_message: None,
+        };
+
+        for candidate in resolve_update_source_candidates(&update_settings, channel) {
+            match check_candidate_for_update(&app, &candidate, ReleaseSelection::Latest(channel))
+                .await
+            {
+                Ok(update) => {
+                    if let Some(update) = update {
+                        entry.is_downgrade = match (
+                            Version::parse(&current_version),
+                            Version::parse(&update.version),
+                        ) {
+                            (Ok(current), Ok(latest)) => {
+                                compare_release_versions(&latest, &current) == Ordering::Less
+                            }
+                            _ => false,
+                        };
+                        entry.is_current = update.version == current_version;
+                        entry.version = Some(update.version.clone());
+                        entry.release_notes = update.body.clone();
+                    }
+                    entry.selected_source = Some(candidate.source.as_str().to_string());
+                    entry.error_category = None;
+                    entry.error_message = None;
+                    break;
+                }
+                Err(failure) => {
+                    entry.error_category = Some(failure.category.as_str().to_string());
+                    entry.error_message = Some(failure.message);
+                }
+            }
+        }
+
+        versions.push(entry);
+    }
+
+    Ok(versions)
 }
 
+/// Install the current channel's update. Moving to an older build (after
+/// switching to a more conservative channel) requires `allow_downgrade`.
 #[tauri::command]
-pub async fn self_update(app: AppHandle) -> Result<(), String> {
+pub async fn self_update(app: AppHandle, allow_downgrade: Option<bool>) -> Result<(), String> {
+    let current_version = current_app_version(&app);
     let update_settings = load_update_settings(&app).await;
-    let candidates = resolve_update_source_candidates(&update_settings);
+    let channel = update_settings.update_channel;
+    let candidates = resolve_update_source_candidates(&update_settings, channel);
     let mut attempted_sources: Vec<UpdateSourceKind> = Vec::new();
     let mut failures: Vec<UpdateSourceFailure> = Vec::new();
 
     for candidate in &candidates {
         push_attempted_source(&mut attempted_sources, candidate.source);
 
-        let update = match check_candidate_for_update(&app, candidate).await {
+        let update = match check_candidate_for_update(
+            &app,
+            candidate,
+            ReleaseSelection::Changes(channel),
+        )
+        .await
+        {
             Ok(Some(update)) => update,
             Ok(None) => {
                 let failure = UpdateSourceFailure::new(
@@ -477,6 +736,23 @@ pub async fn self_update(app: AppHandle) -> Result<(), String> {
             }
         };
 
+        if classify_release_strings(&current_version, &update.version, channel)
+            == ReleaseDirection::Downgrade
+            && !allow_downgrade.unwrap_or(false)
+        {
+            let failure = UpdateSourceFailure::new(
+                candidate.source,
+                UpdateErrorCategory::DowngradeRequiresConfirmation,
+                format!(
+                    "Installing {} from the {} channel would downgrade from {}",
+                    update.version,
+                    channel.as_str(),
+                    current_version
+                ),
+            );
+            return Err(format_failure_message(&failure, &attempted_sources));
+        }
+
         emit_update_progress(
             &app,
             "downloading",
@@ -595,13 +871,18 @@ fn build_update_settings(
                 .map(std::string::ToString::to_string)
                 .collect(),
             fallback_to_official,
+            update_channel: UpdateChannel::Stable,
         }
     }
 
+    fn v(version: &str) -> Version {
+        Version::parse(version).unwrap()
+    }
+
     #[test]
     fn resolve_source_candidates_prefers_selected_source_then_official_fallback() {
         let settings = build_update_settings(UpdateSourceMode::Mirror, true, vec![]);
-        let candidates = resolve_update_source_candidates(&settings);
+        let candidates = resolve_update_source_candidates(&settings, UpdateChannel::Stable);
         let sources: Vec<&str> = candidates
             .iter()
             .map(|candidate| candidate.source.as_str())
@@ -616,7 +897,7 @@ fn resolve_source_candidates_uses_custom_without_official_when_fallback_disabled
             false,
             vec!["https://updates.example.com/latest.json"],
         );
-        let candidates = resolve_update_source_candidates(&settings);
+        let candidates = resolve_update_source_candidates(&settings, UpdateChannel::Stable);
         let sources: Vec<&str> = candidates
             .iter()
             .map(|candidate| candidate.source.as_str())
@@ -631,7 +912,7 @@ fn resolve_source_candidates_uses_custom_without_official_when_fallback_disabled
     #[test]
     fn resolve_source_candidates_keeps_official_single_source() {
         let settings = build_update_settings(UpdateSourceMode::Official, true, vec![]);
-        let candidates = resolve_update_source_candidates(&settings);
+        let candidates = resolve_update_source_candidates(&settings, UpdateChannel::Stable);
         let sources: Vec<&str> = candidates
             .iter()
             .map(|candidate| candidate.source.as_str())
@@ -639,6 +920,89 @@ fn resolve_source_candidates_keeps_official_single_source() {
         assert_eq!(sources, vec!["official"]);
     }
 
+    #[test]
+    fn resolve_source_candidates_use_channel_feeds() {
+        let settings = build_update_settings(
+            UpdateSourceMode::Custom,
+            true,
+            vec!["https://updates.example.com/{{channel}}/latest.json"],
+        );
+        let candidates = resolve_update_source_candidates(&settings, UpdateChannel::Nightly);
+        let urls: Vec<Vec<String>> = candidates
+            .iter()
+            .map(|candidate| match &candidate.strategy {
+                EndpointStrategy::Override(urls) => urls.iter().map(Url::to_string).collect(),
+                EndpointStrategy::Default => Vec::new(),
+            })
+            .collect();
+        assert_eq!(
+            urls,
+            vec![
+                vec!["https://updates.example.com/nightly/latest.json".to_string()],
+                vec![EMBEDDED_NIGHTLY_ENDPOINTS[0].to_string()],
+            ]
+        );
+
+        let stable = resolve_update_source_candidates(&settings, UpdateChannel::Stable);
+        assert!(matches!(stable[1].strategy, EndpointStrategy::Default));
+    }
+
+    #[test]
+    fn release_ordering_ranks_nightly_after_same_stable_release() {
+        assert_eq!(
+            compare_release_versions(&v("1.4.0-nightly.20240601"), &v("1.4.0")),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare_release_versions(&v("1.4.0-nightly.20240601"), &v("1.4.1")),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_release_versions(&v("1.4.0-nightly.20240602"), &v("1.4.0-nightly.20240601")),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare_release_versions(&v("1.4.0-beta.2"), &v("1.4.0")),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_release_versions(&v("1.4.0-beta.2"), &v("1.4.0-nightly.20240601")),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn classify_release_respects_channel_and_flags_downgrades() {
+        // Stable user switching to nightly moves forward.
+        assert_eq!(
+            classify_release(
+                &v("1.4.0"),
+                &v("1.4.0-nightly.20240601"),
+                UpdateChannel::Nightly
+            ),
+            ReleaseDirection::Upgrade
+        );
+        // Stable channel ignores pre-release builds in its feed.
+        assert_eq!(
+            classify_release(&v("1.4.0"), &v("1.5.0-beta.1"), UpdateChannel::Stable),
+            ReleaseDirection::None
+        );
+        // Leaving beta for stable offers the older stable build as a downgrade.
+        assert_eq!(
+            classify_release(&v("1.5.0-beta.1"), &v("1.4.2"), UpdateChannel::Stable),
+            ReleaseDirection::Downgrade
+        );
+        // Staying on beta never offers older builds.
+        assert_eq!(
+            classify_release(&v("1.5.0-beta.2"), &v("1.5.0-beta.1"), UpdateChannel::Beta),
+            ReleaseDirection::None
+        );
+        assert_eq!(
+            classify_release_strings("1.4.0", "not-a-version", UpdateChannel::Stable),
+            ReleaseDirection::None
+        );
+    }
+
     #[test]
     fn categorize_updater_error_distinguishes_core_classes() {
         assert_eq!(
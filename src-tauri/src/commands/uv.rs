@@ -1,7 +1,10 @@
+use crate::core::workspace::glob_to_regex;
 use crate::error::{CogniaError, CogniaResult};
 use crate::platform::process::{self, ProcessOptions};
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 use std::time::Duration;
+use walkdir::WalkDir;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -45,6 +48,27 @@ pub struct UvRunResult {
     pub stderr: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UvWorkspaceMember {
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub requires_python: Option<String>,
+    pub path: String,
+    /// Path relative to the workspace root with `/` separators; empty for the root
+    pub relative_path: String,
+    pub is_root: bool,
+}
+
+/// PEP 723 inline metadata of a standalone script
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UvScriptMetadata {
+    pub has_metadata: bool,
+    pub dependencies: Vec<String>,
+    pub requires_python: Option<String>,
+}
+
 // ── Helper ──
 
 fn make_opts(timeout_secs: u64) -> ProcessOptions {
@@ -131,17 +155,29 @@ pub async fn uv_remove(path: String, packages: Vec<String>, dev: bool) -> Result
         .map_err(|e| e.to_string())
 }
 
+/// Sync the project at `path`. In a workspace, `package` syncs a single
+/// member instead of the root.
 #[tauri::command]
-pub async fn uv_sync(path: String, frozen: bool) -> Result<String, String> {
+pub async fn uv_sync(
+    path: String,
+    frozen: bool,
+    package: Option<String>,
+) -> Result<String, String> {
     let mut args = vec!["sync"];
     if frozen {
         args.push("--frozen");
     }
+    if let Some(ref package) = package {
+        args.push("--package");
+        args.push(package);
+    }
     run_uv(&args, Some(&path), 300)
         .await
         .map_err(|e| e.to_string())
 }
 
+/// Lock the project at `path`. Called on a workspace member, uv locks the
+/// whole workspace the member belongs to.
 #[tauri::command]
 pub async fn uv_lock(path: String, upgrade: bool) -> Result<String, String> {
     let mut args = vec!["lock"];
@@ -170,6 +206,30 @@ pub async fn uv_run(path: String, command: Vec<String>) -> Result<UvRunResult, S
     })
 }
 
+/// Run a standalone script with `uv run --script`, which installs the
+/// dependencies declared in its inline metadata into a cached environment
+#[tauri::command]
+pub async fn uv_run_script(
+    script_path: String,
+    script_args: Vec<String>,
+) -> Result<UvRunResult, String> {
+    let script = Path::new(&script_path);
+    let mut opts = make_opts(600);
+    if let Some(dir) = script.parent().filter(|d| !d.as_os_str().is_empty()) {
+        opts = opts.with_cwd(dir);
+    }
+    let mut args = vec!["run", "--script", script_path.as_str()];
+    args.extend(script_args.iter().map(|s| s.as_str()));
+    let out = process::execute("uv", &args, Some(opts))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(UvRunResult {
+        exit_code: if out.success { 0 } else { 1 },
+        stdout: out.stdout,
+        stderr: out.stderr,
+    })
+}
+
 #[tauri::command]
 pub async fn uv_tree(path: String) -> Result<String, String> {
     run_uv(&["tree"], Some(&path), 60)
@@ -268,6 +328,199 @@ pub async fn uv_python_pin(path: String, version: String) -> Result<String, Stri
         .map_err(|e| e.to_string())
 }
 
+/// Interpreter uv would use for `request` (e.g. `3.12`, `>=3.10`), resolved
+/// from `path` so project pins apply
+#[tauri::command]
+pub async fn uv_python_find(
+    request: Option<String>,
+    path: Option<String>,
+) -> Result<String, String> {
+    let mut args = vec!["python", "find"];
+    if let Some(ref request) = request {
+        args.push(request);
+    }
+    run_uv(&args, path.as_deref(), 30)
+        .await
+        .map(|s| s.trim().to_string())
+        .map_err(|e| e.to_string())
+}
+
+// ── Workspaces and scripts ──
+
+/// Directories never searched for workspace members
+const SKIPPED_MEMBER_DIRS: &[&str] = &[".git", ".venv", "node_modules", "__pycache__", "target"];
+
+fn read_pyproject(path: &Path) -> CogniaResult<toml::Value> {
+    let content = std::fs::read_to_string(path.join("pyproject.toml"))?;
+    toml::from_str(&content).map_err(|e| CogniaError::Parse(e.to_string()))
+}
+
+fn string_list(value: Option<&toml::Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn relative_path(root: &Path, dir: &Path) -> String {
+    dir.strip_prefix(root)
+        .unwrap_or(dir)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn workspace_member(
+    root: &Path,
+    dir: &Path,
+    doc: &toml::Value,
+    is_root: bool,
+) -> UvWorkspaceMember {
+    let project = doc.get("project");
+    let field = |key: &str| {
+        project
+            .and_then(|p| p.get(key))
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+    };
+    UvWorkspaceMember {
+        name: field("name"),
+        version: field("version"),
+        requires_python: field("requires-python"),
+        path: dir.display().to_string(),
+        relative_path: relative_path(root, dir),
+        is_root,
+    }
+}
+
+/// The root project plus every directory matched by
+/// `[tool.uv.workspace] members` and not by `exclude`
+pub fn list_workspace_members(root: &Path) -> CogniaResult<Vec<UvWorkspaceMember>> {
+    let doc = read_pyproject(root)?;
+    let mut members = Vec::new();
+    if doc.get("project").is_some() {
+        members.push(workspace_member(root, root, &doc, true));
+    }
+
+    let Some(workspace) = doc
+        .get("tool")
+        .and_then(|t| t.get("uv"))
+        .and_then(|u| u.get("workspace"))
+    else {
+        return Ok(members);
+    };
+    let includes: Vec<_> = string_list(workspace.get("members"))
+        .iter()
+        .filter_map(|g| glob_to_regex(g.trim_end_matches('/')))
+        .collect();
+    let excludes: Vec<_> = string_list(workspace.get("exclude"))
+        .iter()
+        .filter_map(|g| glob_to_regex(g.trim_end_matches('/')))
+        .collect();
+
+    let mut walker = WalkDir::new(root)
+        .min_depth(1)
+        .max_depth(6)
+        .sort_by_file_name()
+        .into_iter();
+    while let Some(entry) = walker.next() {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        if !entry.file_type().is_dir() {
+            continue;
+        }
+        if SKIPPED_MEMBER_DIRS.contains(&entry.file_name().to_string_lossy().as_ref()) {
+            walker.skip_current_dir();
+            continue;
+        }
+        let dir = entry.path();
+        let relative = relative_path(root, dir);
+        if !includes.iter().any(|re| re.is_match(&relative))
+            || excludes.iter().any(|re| re.is_match(&relative))
+            || !dir.join("pyproject.toml").is_file()
+        {
+            continue;
+        }
+        match read_pyproject(dir) {
+            Ok(member_doc) => members.push(workspace_member(root, dir, &member_doc, false)),
+            Err(e) => log::warn!("Skipping uv workspace member {}: {}", dir.display(), e),
+        }
+    }
+    Ok(members)
+}
+
+/// Parse the `# /// script` block of a PEP 723 script
+pub fn parse_script_metadata(source: &str) -> CogniaResult<UvScriptMetadata> {
+    let mut blocks = Vec::new();
+    let mut current: Option<Vec<&str>> = None;
+    for line in source.lines() {
+        let line = line.trim_end_matches('\r');
+        match current.as_mut() {
+            None if line == "# /// script" => current = Some(Vec::new()),
+            None => {}
+            Some(body) if line == "# ///" => {
+                blocks.push(body.join("\n"));
+                current = None;
+            }
+            Some(body) => match line.strip_prefix("# ") {
+                Some(content) => body.push(content),
+                None if line == "#" => body.push(""),
+                None => {
+                    return Err(CogniaError::Parse(
+                        "Unterminated `# /// script` block".into(),
+                    ))
+                }
+            },
+        }
+    }
+    if current.is_some() {
+        return Err(CogniaError::Parse(
+            "Unterminated `# /// script` block".into(),
+        ));
+    }
+    let Some(block) = blocks.first() else {
+        return Ok(UvScriptMetadata::default());
+    };
+    if blocks.len() > 1 {
+        return Err(CogniaError::Parse("Multiple `# /// script` blocks".into()));
+    }
+
+    let doc: toml::Value = toml::from_str(block).map_err(|e| CogniaError::Parse(e.to_string()))?;
+    Ok(UvScriptMetadata {
+        has_metadata: true,
+        dependencies: string_list(doc.get("dependencies")),
+        requires_python: doc
+            .get("requires-python")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+    })
+}
+
+/// List the projects of the uv workspace rooted at `path`
+#[tauri::command]
+pub async fn uv_workspace_list(path: String) -> Result<Vec<UvWorkspaceMember>, String> {
+    tokio::task::spawn_blocking(move || list_workspace_members(Path::new(&path)))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Dependencies and `requires-python` declared inline in a script
+#[tauri::command]
+pub async fn uv_script_deps(script_path: String) -> Result<UvScriptMetadata, String> {
+    let source = crate::platform::fs::read_file_string(&script_path)
+        .await
+        .map_err(|e| e.to_string())?;
+    parse_script_metadata(&source).map_err(|e| e.to_string())
+}
+
 // ── pip compile (requirements) ──
 
 #[tauri::command]
@@ -365,3 +618,62 @@ pub async fn uv_tool_run(name: String, tool_args: Vec<String>) -> Result<UvRunRe
         stderr: out.stderr,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_script_metadata() {
+        let source = "#!/usr/bin/env -S uv run --script\n\
+# /// script\n\
+# requires-python = \">=3.11\"\n\
+# dependencies = [\n\
+#   \"requests<3\",\n\
+#   \"rich\",\n\
+# ]\n\
+#\n\
+# ///\n\
+import requests\n";
+        let meta = parse_script_metadata(source).unwrap();
+        assert!(meta.has_metadata);
+        assert_eq!(meta.dependencies, vec!["requests<3", "rich"]);
+        assert_eq!(meta.requires_python.as_deref(), Some(">=3.11"));
+
+        let plain = parse_script_metadata("print('hi')\n").unwrap();
+        assert!(!plain.has_metadata);
+        assert!(parse_script_metadata("# /// script\n# dependencies = []\n").is_err());
+    }
+
+    #[test]
+    fn test_list_workspace_members() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let write = |rel: &str, content: &str| {
+            let path = root.join(rel);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write(
+            "pyproject.toml",
+            "[project]\nname = \"app\"\nversion = \"0.1.0\"\n\n\
+             [tool.uv.workspace]\nmembers = [\"packages/*\"]\nexclude = [\"packages/legacy\"]\n",
+        );
+        write(
+            "packages/core/pyproject.toml",
+            "[project]\nname = \"core\"\nrequires-python = \">=3.10\"\n",
+        );
+        write(
+            "packages/legacy/pyproject.toml",
+            "[project]\nname = \"legacy\"\n",
+        );
+        std::fs::create_dir_all(root.join("packages/empty")).unwrap();
+
+        let members = list_workspace_members(root).unwrap();
+        let names: Vec<_> = members.iter().map(|m| m.name.as_deref()).collect();
+        assert_eq!(names, vec![Some("app"), Some("core")]);
+        assert!(members[0].is_root);
+        assert_eq!(members[1].relative_path, "packages/core");
+        assert_eq!(members[1].requires_python.as_deref(), Some(">=3.10"));
+    }
+}
@@ -0,0 +1,102 @@
+use crate::provider::volta::{
+    is_in_volta_bin, volta_bin_dir, volta_home_dir, VoltaProvider, VoltaToolchain,
+};
+use crate::provider::{Provider, SystemPackageProvider};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VoltaStatus {
+    pub available: bool,
+    pub version: Option<String>,
+    pub volta_home: Option<String>,
+    /// Whether `$VOLTA_HOME/bin` is on PATH
+    pub shims_on_path: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VoltaWhichResult {
+    pub tool: String,
+    pub path: Option<String>,
+}
+
+async fn ensure_volta(provider: &VoltaProvider) -> Result<(), String> {
+    if provider.is_available().await {
+        Ok(())
+    } else {
+        Err("volta is not installed or not on PATH (see https://volta.sh)".into())
+    }
+}
+
+/// Report whether Volta is installed and where its home and shims live
+#[tauri::command]
+pub async fn volta_status() -> Result<VoltaStatus, String> {
+    let provider = VoltaProvider::new();
+    let available = provider.is_available().await;
+    let version = if available {
+        provider.get_version().await.ok()
+    } else {
+        None
+    };
+
+    let shims_on_path = volta_bin_dir().is_some_and(|bin| {
+        std::env::var_os("PATH").is_some_and(|path| {
+            std::env::split_paths(&path).any(|entry| is_in_volta_bin(&entry, &bin))
+        })
+    });
+
+    Ok(VoltaStatus {
+        available,
+        version,
+        volta_home: volta_home_dir().map(|p| p.display().to_string()),
+        shims_on_path,
+    })
+}
+
+#[tauri::command]
+pub async fn volta_list() -> Result<Vec<VoltaToolchain>, String> {
+    let provider = VoltaProvider::new();
+    ensure_volta(&provider).await?;
+    provider.list_toolchains().await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn volta_install(tool: String, version: Option<String>) -> Result<String, String> {
+    let provider = VoltaProvider::new();
+    ensure_volta(&provider).await?;
+    provider
+        .install_tool(&tool, version.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Pin a tool version in the project's `package.json`
+#[tauri::command]
+pub async fn volta_pin(
+    tool: String,
+    version: Option<String>,
+    project_path: String,
+) -> Result<String, String> {
+    let provider = VoltaProvider::new();
+    ensure_volta(&provider).await?;
+    provider
+        .pin_tool(&tool, version.as_deref(), &PathBuf::from(project_path))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn volta_which(tool: String) -> Result<VoltaWhichResult, String> {
+    let provider = VoltaProvider::new();
+    ensure_volta(&provider).await?;
+    let path = provider
+        .which_tool(&tool)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(VoltaWhichResult {
+        tool: tool.trim().to_string(),
+        path: path.map(|p| p.display().to_string()),
+    })
+}
@@ -1,5 +1,15 @@
-use crate::provider::winget::{WingetInfo, WingetPin, WingetProvider, WingetSource};
+use crate::commands::config::SharedSettings;
+use crate::commands::environment::{
+    env_detect_system, invalidate_env_caches, SystemEnvironmentInfo,
+};
+use crate::platform::env;
+use crate::provider::winget::{
+    community_source_problem, match_available_version, select_env_package, WingetInfo, WingetPin,
+    WingetProvider, WingetSource, WINGET_COMMUNITY_SOURCE,
+};
+use crate::provider::Provider;
 use crate::SharedRegistry;
+use serde::Serialize;
 use tauri::State;
 
 /// Helper to get the WingetProvider from the registry.
@@ -183,3 +193,141 @@ pub async fn winget_install_advanced(
         .await
         .map_err(|e| e.to_string())
 }
+
+// ── Environment install ─────────────────────────────────────────────────
+
+/// Result of installing an environment type through winget
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WingetEnvInstallResult {
+    pub env_type: String,
+    pub package_id: String,
+    /// Version `winget list` reports after the install
+    pub installed_version: Option<String>,
+    /// Pin added because a specific version was requested
+    pub pinned_version: Option<String>,
+    /// PATH entries picked up from the registry after the install
+    pub path_added: Vec<String>,
+    /// The toolchain as system detection sees it after the install
+    pub detected: Option<SystemEnvironmentInfo>,
+    pub warnings: Vec<String>,
+}
+
+/// Install an environment type through winget and make it show up in
+/// `env_list` right away.
+///
+/// The package comes from the built-in env type table or the
+/// `providers.winget.env_packages` setting, and a version such as `3.11`
+/// selects that release line's package. Requested versions are pinned so
+/// `winget upgrade --all` leaves them alone.
+#[tauri::command]
+pub async fn winget_install_environment(
+    env_type: String,
+    version: Option<String>,
+    registry: State<'_, SharedRegistry>,
+    config: State<'_, SharedSettings>,
+) -> Result<WingetEnvInstallResult, String> {
+    let provider = get_winget(&registry).await?;
+    let overrides = config.read().await.get_winget_env_packages();
+    let selection = select_env_package(&env_type, version.as_deref(), &overrides).ok_or_else(|| {
+        format!(
+            "No winget package is mapped to environment type '{}'. Add one with the providers.winget.env_packages setting.",
+            env_type
+        )
+    })?;
+
+    // Toolchains are installed from the community source only; bail out
+    // early with a fix instead of letting winget report a generic miss
+    if let Ok(sources) = provider.source_list().await {
+        if let Some(problem) = community_source_problem(&sources) {
+            return Err(problem);
+        }
+    }
+
+    let install_version = match &selection.version {
+        Some(requested) => {
+            let available: Vec<String> = provider
+                .get_versions(&selection.package_id)
+                .await
+                .map_err(|e| e.to_string())?
+                .into_iter()
+                .map(|v| v.version)
+                .collect();
+            Some(
+                match_available_version(&available, requested).ok_or_else(|| {
+                    format!(
+                        "{} has no version matching {}",
+                        selection.package_id, requested
+                    )
+                })?,
+            )
+        }
+        None => None,
+    };
+
+    provider
+        .install_from_source(
+            &selection.package_id,
+            install_version.as_deref(),
+            WINGET_COMMUNITY_SOURCE,
+        )
+        .await
+        .map_err(|e| {
+            let message = e.to_string();
+            if message.contains("No package found") {
+                format!(
+                    "{} was not found on the winget source: {}",
+                    selection.package_id, message
+                )
+            } else {
+                message
+            }
+        })?;
+
+    let mut warnings = Vec::new();
+    let pinned_version = match (&selection.version, &install_version) {
+        (Some(requested), Some(resolved)) => {
+            // An exact request pins that release; a partial one (`20`) pins its line
+            let pin = if requested == resolved {
+                resolved.clone()
+            } else {
+                format!("{}.*", requested)
+            };
+            match provider
+                .pin_add(&selection.package_id, Some(&pin), false)
+                .await
+            {
+                Ok(()) => Some(pin),
+                Err(e) => {
+                    warnings.push(format!("Installed, but pinning {} failed: {}", pin, e));
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+
+    let installed_version = provider.installed_version(&selection.package_id).await;
+    let path_added = env::refresh_process_path().await.unwrap_or_else(|e| {
+        warnings.push(format!("Failed to refresh PATH: {}", e));
+        Vec::new()
+    });
+
+    invalidate_env_caches(config.inner()).await;
+    let detected = env_detect_system(selection.env_type.clone(), Some(true), config)
+        .await
+        .unwrap_or_else(|e| {
+            warnings.push(format!("Detection after install failed: {}", e));
+            None
+        });
+
+    Ok(WingetEnvInstallResult {
+        env_type: selection.env_type,
+        package_id: selection.package_id,
+        installed_version,
+        pinned_version,
+        path_added,
+        detected,
+        warnings,
+    })
+}
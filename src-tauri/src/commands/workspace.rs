@@ -0,0 +1,255 @@
+use crate::commands::config::SharedSettings;
+use crate::commands::environment::{
+    enabled_detection_sources_for_env_type, run_env_install, CancellationTokens, SharedRegistry,
+};
+use crate::core::operation_queue::{JobKind, SharedOperationQueue};
+use crate::core::workspace::{
+    missing_pins, plan_missing_toolchains, scan_workspace, Workspace, WorkspaceApplyResult,
+    WorkspaceManager, WorkspaceRepoStatus, WorkspaceScanResult, WorkspaceStatus,
+    WorkspaceToolchainError,
+};
+use crate::core::EnvironmentManager;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tauri::{AppHandle, State};
+use tokio::sync::RwLock;
+
+pub type SharedWorkspaceManager = Arc<RwLock<WorkspaceManager>>;
+
+async fn get_workspace(manager: &SharedWorkspaceManager, id: &str) -> Result<Workspace, String> {
+    manager
+        .read()
+        .await
+        .get(id)
+        .cloned()
+        .ok_or_else(|| format!("Workspace '{}' not found", id))
+}
+
+/// Installed versions keyed by logical env type
+async fn installed_versions(
+    registry: &SharedRegistry,
+) -> Result<HashMap<String, Vec<String>>, String> {
+    let environments = EnvironmentManager::new(registry.clone())
+        .list_environments()
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut installed: HashMap<String, Vec<String>> = HashMap::new();
+    for env in environments {
+        installed
+            .entry(env.env_type)
+            .or_default()
+            .extend(env.installed_versions.into_iter().map(|v| v.version));
+    }
+    Ok(installed)
+}
+
+/// List all workspaces
+#[tauri::command]
+pub async fn workspace_list(
+    manager: State<'_, SharedWorkspaceManager>,
+) -> Result<Vec<Workspace>, String> {
+    Ok(manager.read().await.list().to_vec())
+}
+
+/// Create a workspace from one or more root directories
+#[tauri::command]
+pub async fn workspace_create(
+    name: String,
+    root_paths: Vec<String>,
+    exclude_globs: Option<Vec<String>>,
+    manager: State<'_, SharedWorkspaceManager>,
+) -> Result<Workspace, String> {
+    let mut manager = manager.write().await;
+    let workspace = manager
+        .create(
+            Workspace::new(name, root_paths).with_exclude_globs(exclude_globs.unwrap_or_default()),
+        )
+        .map_err(|e| e.to_string())?;
+    manager.save().await.map_err(|e| e.to_string())?;
+    Ok(workspace)
+}
+
+/// Delete a workspace
+#[tauri::command]
+pub async fn workspace_delete(
+    id: String,
+    manager: State<'_, SharedWorkspaceManager>,
+) -> Result<(), String> {
+    let mut manager = manager.write().await;
+    manager.delete(&id).map_err(|e| e.to_string())?;
+    manager.save().await.map_err(|e| e.to_string())
+}
+
+/// Discover the workspace's git repos and detect their project env pins.
+/// Repos whose HEAD and manifests are unchanged since the last scan keep
+/// their pins; pass `full` to detect everything again.
+#[tauri::command]
+pub async fn workspace_scan(
+    id: String,
+    full: Option<bool>,
+    config: State<'_, SharedSettings>,
+    manager: State<'_, SharedWorkspaceManager>,
+) -> Result<WorkspaceScanResult, String> {
+    let workspace = get_workspace(manager.inner(), &id).await?;
+
+    let mut sources = Vec::new();
+    for env in crate::provider::SystemEnvironmentType::all() {
+        let env_type = env.env_type();
+        let enabled = enabled_detection_sources_for_env_type(env_type, config.inner()).await;
+        sources.push((env_type.to_string(), enabled));
+    }
+
+    let (repos, result) = scan_workspace(&workspace, &sources, full.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut manager = manager.write().await;
+    manager.set_repos(&id, repos).map_err(|e| e.to_string())?;
+    manager.save().await.map_err(|e| e.to_string())?;
+    Ok(result)
+}
+
+/// Pins, missing toolchains and ahead/behind counts for every repo found
+/// by the last scan
+#[tauri::command]
+pub async fn workspace_status(
+    id: String,
+    registry: State<'_, SharedRegistry>,
+    manager: State<'_, SharedWorkspaceManager>,
+) -> Result<WorkspaceStatus, String> {
+    let workspace = get_workspace(manager.inner(), &id).await?;
+    let installed = installed_versions(registry.inner()).await?;
+
+    let statuses = workspace.repos.iter().map(|repo| {
+        let installed = &installed;
+        async move {
+            let (ahead_behind, git_error) = match &repo.branch {
+                Some(branch) => {
+                    match crate::commands::git::git_get_ahead_behind(
+                        repo.path.clone(),
+                        branch.clone(),
+                        None,
+                    )
+                    .await
+                    {
+                        Ok(counts) => (Some(counts), None),
+                        Err(e) => (None, Some(e)),
+                    }
+                }
+                None => (None, None),
+            };
+            WorkspaceRepoStatus {
+                path: repo.path.clone(),
+                name: repo.name.clone(),
+                branch: repo.branch.clone(),
+                pins: repo.pins.clone(),
+                missing: missing_pins(repo, installed),
+                ahead_behind,
+                git_error,
+            }
+        }
+    });
+    let repos = futures::future::join_all(statuses).await;
+
+    Ok(WorkspaceStatus {
+        workspace_id: workspace.id,
+        name: workspace.name,
+        last_scanned_at: workspace.last_scanned_at,
+        repos,
+        missing_toolchains: plan_missing_toolchains(&workspace.repos, &installed),
+    })
+}
+
+/// Install every toolchain the workspace's repos pin but that is not
+/// installed, as one job. Each version is installed once even when several
+/// repos pin it, so shared artifacts are downloaded a single time and
+/// served from the download cache afterwards.
+#[tauri::command]
+pub async fn workspace_apply_envs(
+    id: String,
+    registry: State<'_, SharedRegistry>,
+    tokens: State<'_, CancellationTokens>,
+    config: State<'_, SharedSettings>,
+    jobs: State<'_, SharedOperationQueue>,
+    manager: State<'_, SharedWorkspaceManager>,
+    app: AppHandle,
+) -> Result<WorkspaceApplyResult, String> {
+    let workspace = get_workspace(manager.inner(), &id).await?;
+    let installed = installed_versions(registry.inner()).await?;
+    let plan = plan_missing_toolchains(&workspace.repos, &installed);
+
+    let mut result = WorkspaceApplyResult {
+        workspace_id: workspace.id.clone(),
+        installed: Vec::new(),
+        failed: Vec::new(),
+        skipped: Vec::new(),
+    };
+    if plan.is_empty() {
+        return Ok(result);
+    }
+
+    let cancel_token = Arc::new(AtomicBool::new(false));
+    let job = jobs
+        .inner()
+        .start(
+            JobKind::BatchInstall,
+            format!(
+                "Install {} toolchain(s) for workspace {}",
+                plan.len(),
+                workspace.name
+            ),
+            serde_json::json!({
+                "workspaceId": workspace.id,
+                "toolchains": plan,
+            }),
+            Some(cancel_token.clone()),
+        )
+        .await;
+
+    let total = plan.len();
+    for (index, toolchain) in plan.into_iter().enumerate() {
+        if cancel_token.load(Ordering::SeqCst) {
+            result.skipped.push(toolchain);
+            continue;
+        }
+        job.progress(
+            Some(index as f64 / total as f64 * 100.0),
+            Some(format!("{} {}", toolchain.env_type, toolchain.version)),
+        );
+        match run_env_install(
+            toolchain.env_type.clone(),
+            toolchain.version.clone(),
+            None,
+            registry.clone(),
+            tokens.clone(),
+            config.clone(),
+            app.clone(),
+            cancel_token.clone(),
+            None,
+        )
+        .await
+        {
+            Ok(()) => result.installed.push(toolchain),
+            Err(error) => result.failed.push(WorkspaceToolchainError {
+                env_type: toolchain.env_type,
+                version: toolchain.version,
+                error,
+            }),
+        }
+    }
+
+    let outcome = if cancel_token.load(Ordering::SeqCst) {
+        Err("Installation cancelled by user".to_string())
+    } else if result.failed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} of {} toolchain(s) failed",
+            result.failed.len(),
+            total
+        ))
+    };
+    job.finish(&outcome).await;
+    Ok(result)
+}
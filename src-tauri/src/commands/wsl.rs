@@ -1,9 +1,22 @@
+use crate::core::operation_queue::{JobKind, SharedOperationQueue};
 use crate::platform::EnvVarScope;
 use crate::provider::wsl::{
-    WslCapabilities, WslDistroResources, WslPackageUpdateResult, WslProvider, WslUser,
+    WslBatchItemResult, WslBatchOptions, WslBatchStatus, WslCapabilities, WslDistroResources,
+    WslPackageUpdateResult, WslProvider, WslUser,
+};
+use crate::provider::wsl_config::{
+    self, WslConfigContext, WslConfigDiagnostic, WslConfigKeyDoc, WslConfigKind, WslConfigSeverity,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Default per-distro timeout of `wsl_batch_exec`
+const BATCH_EXEC_TIMEOUT_SECS: u64 = 120;
+/// Default per-distro timeout of `wsl_batch_update`; upgrades can be slow
+const BATCH_UPDATE_TIMEOUT_SECS: u64 = 1800;
 
 /// WSL distribution info returned to the frontend
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -832,14 +845,28 @@ pub async fn wsl_export(
     name: String,
     file_path: String,
     as_vhd: Option<bool>,
+    jobs: State<'_, SharedOperationQueue>,
 ) -> Result<(), String> {
     let provider = get_provider();
     ensure_runtime_available(&provider, "distro.export").await?;
     ensure_distro_exists(&provider, "distro.export", &name).await?;
-    provider
-        .export_distro(&name, &file_path, as_vhd.unwrap_or(false))
+
+    let job = jobs
+        .inner()
+        .start(
+            JobKind::WslExport,
+            format!("Export {} to {}", name, file_path),
+            serde_json::json!({ "name": name, "filePath": file_path, "asVhd": as_vhd }),
+            None,
+        )
+        .await;
+    let cancel = job.cancel_flag();
+    let result = provider
+        .export_distro_cancellable(&name, &file_path, as_vhd.unwrap_or(false), Some(&cancel))
         .await
-        .map_err(|e| normalize_wsl_error(e.to_string()))
+        .map_err(|e| normalize_wsl_error(e.to_string()));
+    job.finish(&result).await;
+    result
 }
 
 /// Import a WSL distribution from a file
@@ -1047,6 +1074,129 @@ pub async fn wsl_set_config(
     }
 }
 
+/// Outcome of a single-key config edit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WslConfigSetKeyResult {
+    /// Warnings about the written key, e.g. a limit above the host's resources
+    pub diagnostics: Vec<WslConfigDiagnostic>,
+    /// `.wslconfig` changes only apply after `wsl --shutdown`
+    pub shutdown_recommended: bool,
+    /// `wsl.conf` changes apply the next time this distribution starts
+    pub restart_distro: Option<String>,
+}
+
+/// Installed WSL version and host resources the config is checked against
+async fn wsl_config_context(provider: &WslProvider) -> WslConfigContext {
+    let wsl_version = provider
+        .get_full_version_info()
+        .await
+        .ok()
+        .and_then(|info| info.wsl_version);
+    let platform = crate::core::system_info::collect_platform_info(false).await;
+    WslConfigContext {
+        wsl_version,
+        host_memory_bytes: Some(platform.total_memory).filter(|bytes| *bytes > 0),
+        host_processors: Some(platform.cpu_cores).filter(|cores| *cores > 0),
+    }
+}
+
+/// Known sections and keys of `.wslconfig` (`global`) or `/etc/wsl.conf` (`distro`)
+#[tauri::command]
+pub async fn wsl_config_schema(kind: WslConfigKind) -> Result<Vec<WslConfigKeyDoc>, String> {
+    Ok(wsl_config::schema_docs(kind))
+}
+
+/// Check config content key by key without writing it
+#[tauri::command]
+pub async fn wsl_config_validate(
+    content: String,
+    kind: WslConfigKind,
+) -> Result<Vec<WslConfigDiagnostic>, String> {
+    let context = wsl_config_context(&get_provider()).await;
+    Ok(wsl_config::validate(&content, kind, &context))
+}
+
+/// Set or remove (`value: None`) one key in `.wslconfig` or a distribution's
+/// `/etc/wsl.conf`, keeping comments and unknown keys. Values WSL would
+/// reject are refused; warnings are returned with the result.
+#[tauri::command]
+pub async fn wsl_config_set_key(
+    kind: WslConfigKind,
+    distro: Option<String>,
+    section: String,
+    key: String,
+    value: Option<String>,
+) -> Result<WslConfigSetKeyResult, String> {
+    let provider = get_provider();
+    let content = match kind {
+        WslConfigKind::Global => {
+            ensure_runtime_available(&provider, "runtime.setConfig").await?;
+            WslProvider::read_wslconfig_content().map_err(|e| normalize_wsl_error(e.to_string()))?
+        }
+        WslConfigKind::Distro => {
+            let distro = distro.as_deref().ok_or_else(|| {
+                runtime_precondition_error(
+                    "distro.setConfig",
+                    "A distribution is required to edit wsl.conf.",
+                )
+            })?;
+            ensure_runtime_available(&provider, "distro.setConfig").await?;
+            ensure_distro_exists(&provider, "distro.setConfig", distro).await?;
+            provider
+                .read_distro_config_content(distro)
+                .await
+                .map_err(|e| normalize_wsl_error(e.to_string()))?
+        }
+    };
+
+    let updated = wsl_config::set_key(&content, &section, &key, value.as_deref())
+        .map_err(|e| e.to_string())?;
+    let context = wsl_config_context(&provider).await;
+    let diagnostics: Vec<WslConfigDiagnostic> = wsl_config::validate(&updated, kind, &context)
+        .into_iter()
+        .filter(|d| {
+            d.section
+                .as_deref()
+                .is_some_and(|s| s.eq_ignore_ascii_case(&section))
+        })
+        .filter(|d| {
+            d.key
+                .as_deref()
+                .is_some_and(|k| k.eq_ignore_ascii_case(&key))
+        })
+        .collect();
+    if let Some(error) = diagnostics
+        .iter()
+        .find(|d| d.severity == WslConfigSeverity::Error)
+    {
+        return Err(normalize_wsl_error(error.message.clone()));
+    }
+
+    match (kind, distro) {
+        (WslConfigKind::Distro, Some(distro)) => {
+            provider
+                .write_distro_config_content(&distro, &updated)
+                .await
+                .map_err(|e| normalize_wsl_error(e.to_string()))?;
+            Ok(WslConfigSetKeyResult {
+                diagnostics,
+                shutdown_recommended: false,
+                restart_distro: Some(distro),
+            })
+        }
+        _ => {
+            WslProvider::write_wslconfig_content(&updated)
+                .map_err(|e| normalize_wsl_error(e.to_string()))?;
+            Ok(WslConfigSetKeyResult {
+                diagnostics,
+                shutdown_recommended: true,
+                restart_distro: None,
+            })
+        }
+    }
+}
+
 /// Set the global WSL networking mode in .wslconfig [wsl2].
 #[tauri::command]
 pub async fn wsl_set_networking_mode(mode: String) -> Result<(), String> {
@@ -1394,6 +1544,122 @@ pub async fn wsl_batch_terminate(
     Ok(provider.batch_terminate(&names).await)
 }
 
+/// Full output of batch runs that is too long to return inline
+fn batch_spill_dir(app: &AppHandle) -> PathBuf {
+    app.path()
+        .app_log_dir()
+        .unwrap_or_else(|_| std::env::temp_dir())
+        .join("wsl-batch")
+}
+
+fn ensure_batch_targets(distros: &[String]) -> Result<(), String> {
+    if distros.is_empty() {
+        return Err("No distributions selected".into());
+    }
+    Ok(())
+}
+
+/// Run a shell command in several distributions, concurrently when `parallel`.
+/// Stopped distributions are skipped unless `auto_start`, in which case they
+/// are started for the run and stopped again afterwards.
+#[tauri::command]
+pub async fn wsl_batch_exec(
+    distros: Vec<String>,
+    command: String,
+    parallel: bool,
+    user: Option<String>,
+    timeout_secs: Option<u64>,
+    auto_start: Option<bool>,
+    app: AppHandle,
+) -> Result<Vec<WslBatchItemResult>, String> {
+    let provider = get_provider();
+    ensure_runtime_available(&provider, "distro.batchExec").await?;
+    ensure_batch_targets(&distros)?;
+    let options = WslBatchOptions {
+        timeout: Duration::from_secs(timeout_secs.unwrap_or(BATCH_EXEC_TIMEOUT_SECS)),
+        auto_start: auto_start.unwrap_or(false),
+        user,
+        spill_dir: batch_spill_dir(&app),
+    };
+    Ok(provider
+        .batch_exec(&distros, &command, parallel, &options)
+        .await)
+}
+
+/// Refresh and upgrade packages in several distributions at once, each with
+/// the package manager detected from its `/etc/os-release`.
+#[tauri::command]
+pub async fn wsl_batch_update(
+    distros: Vec<String>,
+    timeout_secs: Option<u64>,
+    auto_start: Option<bool>,
+    app: AppHandle,
+) -> Result<Vec<WslBatchItemResult>, String> {
+    let provider = get_provider();
+    ensure_runtime_available(&provider, "distro.batchUpdate").await?;
+    ensure_batch_targets(&distros)?;
+    let options = WslBatchOptions {
+        timeout: Duration::from_secs(timeout_secs.unwrap_or(BATCH_UPDATE_TIMEOUT_SECS)),
+        auto_start: auto_start.unwrap_or(false),
+        user: None,
+        spill_dir: batch_spill_dir(&app),
+    };
+    Ok(provider.batch_update(&distros, &options).await)
+}
+
+/// Back up several distributions to timestamped tar files in `dest_dir`.
+/// Emits `wsl-batch-export-progress` and runs as one cancellable job.
+#[tauri::command]
+pub async fn wsl_batch_export(
+    distros: Vec<String>,
+    dest_dir: String,
+    app: AppHandle,
+    jobs: State<'_, SharedOperationQueue>,
+) -> Result<Vec<WslBatchItemResult>, String> {
+    let provider = get_provider();
+    ensure_runtime_available(&provider, "distro.batchExport").await?;
+    ensure_batch_targets(&distros)?;
+
+    let job = jobs
+        .inner()
+        .start(
+            JobKind::WslExport,
+            format!("Export {} distributions to {}", distros.len(), dest_dir),
+            serde_json::json!({ "names": distros, "destDir": dest_dir }),
+            None,
+        )
+        .await;
+    let results = provider
+        .batch_export(
+            &distros,
+            &dest_dir,
+            |progress| {
+                let _ = app.emit("wsl-batch-export-progress", &progress);
+                job.progress(
+                    Some(progress.completed as f64 * 100.0 / progress.total as f64),
+                    Some(match progress.status {
+                        None => format!("Exporting {}", progress.distro),
+                        Some(_) => format!("Exported {}/{}", progress.completed, progress.total),
+                    }),
+                );
+            },
+            || job.is_cancelled(),
+        )
+        .await;
+
+    let failed = results
+        .iter()
+        .filter(|r| r.status != WslBatchStatus::Succeeded)
+        .count();
+    let outcome = if failed == 0 {
+        Ok(())
+    } else {
+        Err(format!("{} of {} exports failed", failed, results.len()))
+    };
+    job.finish(&outcome).await;
+    Ok(results)
+}
+
 /// Backup a WSL distribution to a timestamped tar file.
 #[tauri::command]
 pub async fn wsl_backup_distro(
@@ -1,7 +1,11 @@
+use crate::cache::external;
 use crate::error::{CogniaError, CogniaResult};
+use crate::platform::disk::format_size;
 use crate::platform::process::{self, ProcessOptions};
+use crate::provider::xmake::XmakeProvider;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -236,3 +240,464 @@ pub async fn xmake_download_source(name: String, output_dir: Option<String>) ->
         Err(CogniaError::Provider(out.stderr))
     }
 }
+
+// ── Project Dependencies ──
+
+/// Upper bound on packages queried while following transitive deps
+const MAX_PROJECT_PACKAGES: usize = 256;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XmakeDependency {
+    pub name: String,
+    /// Constraint from `add_requires`, e.g. `1.2.x`
+    pub constraint: Option<String>,
+    pub version: Option<String>,
+    /// Listed in the project's `add_requires`
+    pub direct: bool,
+    pub installed: bool,
+    pub install_dir: Option<String>,
+    /// Names of the packages this one depends on
+    pub dependencies: Vec<String>,
+}
+
+/// Dependency graph of an xmake project. Direct packages are the roots.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XmakeProjectDeps {
+    pub project_dir: String,
+    pub packages: Vec<XmakeDependency>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum XmakeDepsExportFormat {
+    Json,
+    Dot,
+}
+
+/// Fields of one `require(...)` block in `xmake require --info` output
+#[derive(Debug, Default, PartialEq)]
+struct RequireInfo {
+    name: String,
+    version: Option<String>,
+    install_dir: Option<String>,
+    deps: Vec<String>,
+}
+
+fn project_dir(path: &str) -> CogniaResult<PathBuf> {
+    let dir = PathBuf::from(path);
+    if !dir.join("xmake.lua").is_file() {
+        return Err(CogniaError::ProjectNotFound(format!(
+            "no xmake.lua in {}",
+            dir.display()
+        )));
+    }
+    Ok(dir)
+}
+
+/// Package name of a require spec: `"zlib 1.2.x"` -> `zlib`, `"conan::fmt 9.x"` -> `conan::fmt`
+fn spec_name(spec: &str) -> &str {
+    spec.split_whitespace().next().unwrap_or(spec)
+}
+
+/// `(name, constraint)` for every string passed to `add_requires(...)`.
+/// Option tables (`{configs = ...}`) are skipped.
+fn parse_add_requires(source: &str) -> Vec<(String, Option<String>)> {
+    let mut requires = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find("add_requires(") {
+        rest = &rest[start + "add_requires(".len()..];
+        let (mut depth, mut brace_depth) = (1usize, 0usize);
+        let mut quote: Option<char> = None;
+        let mut literal = String::new();
+        let mut end = rest.len();
+        for (i, ch) in rest.char_indices() {
+            if let Some(q) = quote {
+                if ch == q {
+                    quote = None;
+                    if brace_depth == 0 {
+                        let spec = literal.trim();
+                        if !spec.is_empty() {
+                            let name = spec_name(spec).to_string();
+                            let constraint = spec[name.len()..].trim();
+                            requires.push((
+                                name,
+                                (!constraint.is_empty()).then(|| constraint.to_string()),
+                            ));
+                        }
+                    }
+                    literal.clear();
+                } else {
+                    literal.push(ch);
+                }
+                continue;
+            }
+            match ch {
+                '"' | '\'' => quote = Some(ch),
+                '{' => brace_depth += 1,
+                '}' => brace_depth = brace_depth.saturating_sub(1),
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        end = i;
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        rest = &rest[end..];
+    }
+    requires
+}
+
+/// Parse `xmake require --info` output:
+/// ```text
+/// The package info of project:
+///     require(zlib 1.2.x):
+///       -> version: v1.2.13
+///       -> installdir: /home/user/.xmake/packages/z/zlib/v1.2.13/abc
+///       -> deps: cmake
+/// ```
+fn parse_require_info(output: &str) -> Vec<RequireInfo> {
+    let mut infos: Vec<RequireInfo> = Vec::new();
+    for line in output.lines() {
+        let trimmed = XmakeProvider::strip_xmake_colors(line.trim());
+        if let Some(spec) = trimmed
+            .strip_prefix("require(")
+            .and_then(|rest| rest.split_once(')'))
+            .map(|(spec, _)| spec.trim())
+        {
+            infos.push(RequireInfo {
+                name: spec_name(spec).to_string(),
+                ..Default::default()
+            });
+            continue;
+        }
+        let (Some(info), Some(field)) = (infos.last_mut(), trimmed.strip_prefix("->")) else {
+            continue;
+        };
+        let field = field.trim();
+        if let Some(value) = field.strip_prefix("version:") {
+            let value = value.trim().trim_start_matches('v');
+            if !value.is_empty() {
+                info.version = Some(value.to_string());
+            }
+        } else if let Some(value) = field.strip_prefix("installdir:") {
+            let value = value.trim();
+            if !value.is_empty() {
+                info.install_dir = Some(value.to_string());
+            }
+        } else if let Some(value) = field.strip_prefix("deps:") {
+            info.deps = value
+                .split([',', ' '])
+                .map(|dep| spec_name(dep.trim()).to_string())
+                .filter(|dep| !dep.is_empty())
+                .collect();
+        }
+    }
+    infos
+}
+
+async fn query_require_info(dir: &Path, names: &[String]) -> CogniaResult<Vec<RequireInfo>> {
+    let opts = make_long_opts().with_cwd(dir.to_string_lossy().to_string());
+    let mut args = vec!["require", "--info"];
+    args.extend(names.iter().map(String::as_str));
+    let out = process::execute("xmake", &args, Some(opts)).await?;
+    if !out.success {
+        return Err(CogniaError::Provider(format!(
+            "{}\n{}",
+            out.stdout, out.stderr
+        )));
+    }
+    Ok(parse_require_info(&format!(
+        "{}\n{}",
+        out.stdout, out.stderr
+    )))
+}
+
+/// Build the graph from the project's requires and the queried package info
+fn build_project_deps(
+    dir: &Path,
+    requires: &[(String, Option<String>)],
+    infos: &HashMap<String, RequireInfo>,
+) -> XmakeProjectDeps {
+    let direct: HashMap<&str, Option<&String>> = requires
+        .iter()
+        .map(|(name, constraint)| (name.as_str(), constraint.as_ref()))
+        .collect();
+    let mut names: Vec<&String> = infos.keys().collect();
+    names.extend(requires.iter().map(|(name, _)| name));
+    names.sort();
+    names.dedup();
+
+    let packages = names
+        .into_iter()
+        .map(|name| {
+            let info = infos.get(name);
+            let install_dir = info.and_then(|info| info.install_dir.clone());
+            XmakeDependency {
+                name: name.clone(),
+                constraint: direct.get(name.as_str()).copied().flatten().cloned(),
+                version: info.and_then(|info| info.version.clone()),
+                direct: direct.contains_key(name.as_str()),
+                installed: install_dir
+                    .as_deref()
+                    .is_some_and(|dir| Path::new(dir).exists()),
+                install_dir,
+                dependencies: info.map(|info| info.deps.clone()).unwrap_or_default(),
+            }
+        })
+        .collect();
+
+    XmakeProjectDeps {
+        project_dir: dir.to_string_lossy().to_string(),
+        packages,
+    }
+}
+
+fn render_dot(deps: &XmakeProjectDeps) -> String {
+    let mut dot = String::from("digraph xmake_deps {\n    rankdir=LR;\n    node [shape=box];\n");
+    for package in &deps.packages {
+        let label = match &package.version {
+            Some(version) => format!("{}\\n{}", package.name, version),
+            None => package.name.clone(),
+        };
+        let mut attrs = vec![format!("label=\"{}\"", label.replace('"', "\\\""))];
+        if package.direct {
+            attrs.push("penwidth=2".into());
+        }
+        if !package.installed {
+            attrs.push("style=dashed".into());
+        }
+        dot.push_str(&format!(
+            "    \"{}\" [{}];\n",
+            package.name.replace('"', "\\\""),
+            attrs.join(", ")
+        ));
+    }
+    for package in &deps.packages {
+        for dep in &package.dependencies {
+            dot.push_str(&format!(
+                "    \"{}\" -> \"{}\";\n",
+                package.name.replace('"', "\\\""),
+                dep.replace('"', "\\\"")
+            ));
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// Dependency tree of the xmake project at `path`: direct requires from
+/// `xmake.lua`, their transitive deps from `xmake require --info`, and
+/// whether each package is installed.
+#[tauri::command]
+pub async fn xmake_project_deps(path: String) -> CogniaResult<XmakeProjectDeps> {
+    let dir = project_dir(&path)?;
+    let source = tokio::fs::read_to_string(dir.join("xmake.lua")).await?;
+    let requires = parse_add_requires(&source);
+
+    // Project requires first, then follow deps breadth-first
+    let mut infos: HashMap<String, RequireInfo> = HashMap::new();
+    for info in query_require_info(&dir, &[]).await? {
+        infos.insert(info.name.clone(), info);
+    }
+    let mut queued: HashSet<String> = infos.keys().cloned().collect();
+    queued.extend(requires.iter().map(|(name, _)| name.clone()));
+    let mut pending: VecDeque<String> = infos
+        .values()
+        .flat_map(|info| info.deps.iter().cloned())
+        .chain(requires.iter().map(|(name, _)| name.clone()))
+        .filter(|name| !infos.contains_key(name))
+        .collect();
+    queued.extend(pending.iter().cloned());
+
+    while let Some(name) = pending.pop_front() {
+        if infos.contains_key(&name) || infos.len() >= MAX_PROJECT_PACKAGES {
+            continue;
+        }
+        let Ok(found) = query_require_info(&dir, std::slice::from_ref(&name)).await else {
+            continue;
+        };
+        for info in found {
+            for dep in &info.deps {
+                if queued.insert(dep.clone()) {
+                    pending.push_back(dep.clone());
+                }
+            }
+            infos.entry(info.name.clone()).or_insert(info);
+        }
+    }
+
+    Ok(build_project_deps(&dir, &requires, &infos))
+}
+
+/// Render the project's dependency graph as JSON or Graphviz DOT
+#[tauri::command]
+pub async fn xmake_project_deps_export(
+    path: String,
+    format: XmakeDepsExportFormat,
+) -> CogniaResult<String> {
+    let deps = xmake_project_deps(path).await?;
+    match format {
+        XmakeDepsExportFormat::Json => {
+            serde_json::to_string_pretty(&deps).map_err(|e| CogniaError::Parse(e.to_string()))
+        }
+        XmakeDepsExportFormat::Dot => Ok(render_dot(&deps)),
+    }
+}
+
+// ── Cache Statistics ──
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XmakeCacheDir {
+    /// `cache` (downloaded sources) or `packages` (installed builds)
+    pub kind: String,
+    pub path: String,
+    pub exists: bool,
+    pub size: u64,
+    pub size_human: String,
+    /// Number of files under the directory
+    pub entry_count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XmakeCacheStats {
+    pub dirs: Vec<XmakeCacheDir>,
+    pub total_size: u64,
+    pub total_size_human: String,
+    pub total_entries: u64,
+}
+
+fn scan_cache_dir(kind: &str, path: PathBuf) -> XmakeCacheDir {
+    let (mut size, mut entry_count) = (0u64, 0u64);
+    for entry in walkdir::WalkDir::new(&path)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+    {
+        size += entry.metadata().map(|m| m.len()).unwrap_or(0);
+        entry_count += 1;
+    }
+    XmakeCacheDir {
+        kind: kind.to_string(),
+        exists: path.is_dir(),
+        path: path.to_string_lossy().to_string(),
+        size,
+        size_human: format_size(size),
+        entry_count,
+    }
+}
+
+/// Size and file count of xmake's package cache and install directories
+#[tauri::command]
+pub async fn xmake_cache_stats() -> CogniaResult<XmakeCacheStats> {
+    let mut locations: BTreeMap<&str, PathBuf> = BTreeMap::new();
+    if let Some(path) = external::get_xmake_cache_path() {
+        locations.insert("cache", path);
+    }
+    if let Some(path) = XmakeProvider::get_xmake_packages_dir() {
+        locations.insert("packages", path);
+    }
+
+    let dirs = tokio::task::spawn_blocking(move || {
+        locations
+            .into_iter()
+            .map(|(kind, path)| scan_cache_dir(kind, path))
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| CogniaError::Internal(e.to_string()))?;
+
+    let total_size = dirs.iter().map(|dir| dir.size).sum();
+    Ok(XmakeCacheStats {
+        total_entries: dirs.iter().map(|dir| dir.entry_count).sum(),
+        total_size_human: format_size(total_size),
+        total_size,
+        dirs,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_add_requires() {
+        let source = r#"
+add_rules("mode.debug", "mode.release")
+add_requires("zlib 1.2.x", "libpng")
+add_requires("fmt", {configs = {header_only = "true"}})
+add_requires('conan::openssl 3.x', {system = false})
+target("demo")
+    add_packages("zlib", "libpng", "fmt")
+"#;
+        assert_eq!(
+            parse_add_requires(source),
+            vec![
+                ("zlib".to_string(), Some("1.2.x".to_string())),
+                ("libpng".to_string(), None),
+                ("fmt".to_string(), None),
+                ("conan::openssl".to_string(), Some("3.x".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_require_info() {
+        let output = "The package info of project:
+    require(libpng):
+      -> description: The official PNG reference library
+      -> version: v1.6.40
+      -> installdir: /home/u/.xmake/packages/l/libpng/v1.6.40/abc
+      -> deps: zlib
+    require(zlib 1.2.x):
+      -> version: ${green}v1.2.13${clear}
+";
+        let infos = parse_require_info(output);
+        assert_eq!(infos.len(), 2);
+        assert_eq!(infos[0].name, "libpng");
+        assert_eq!(infos[0].version.as_deref(), Some("1.6.40"));
+        assert_eq!(infos[0].deps, vec!["zlib"]);
+        assert_eq!(infos[1].name, "zlib");
+        assert_eq!(infos[1].version.as_deref(), Some("1.2.13"));
+        assert!(infos[1].install_dir.is_none());
+    }
+
+    #[test]
+    fn test_build_project_deps_marks_direct_and_transitive() {
+        let requires = vec![("libpng".to_string(), Some("1.6.x".to_string()))];
+        let mut infos = HashMap::new();
+        for info in parse_require_info(
+            "require(libpng):\n  -> version: v1.6.40\n  -> deps: zlib\nrequire(zlib):\n  -> version: v1.3.1\n",
+        ) {
+            infos.insert(info.name.clone(), info);
+        }
+        let deps = build_project_deps(Path::new("/proj"), &requires, &infos);
+
+        assert_eq!(deps.packages.len(), 2);
+        let libpng = &deps.packages[0];
+        assert!(libpng.direct);
+        assert_eq!(libpng.constraint.as_deref(), Some("1.6.x"));
+        assert_eq!(libpng.dependencies, vec!["zlib"]);
+        assert!(!libpng.installed);
+        assert!(!deps.packages[1].direct);
+
+        let dot = render_dot(&deps);
+        assert!(dot.starts_with("digraph xmake_deps {"));
+        assert!(dot.contains("\"libpng\" -> \"zlib\";"));
+        assert!(dot.contains("penwidth=2"));
+    }
+
+    #[test]
+    fn test_project_without_xmake_lua_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = project_dir(&dir.path().to_string_lossy()).unwrap_err();
+        assert!(matches!(err, CogniaError::ProjectNotFound(_)));
+    }
+}
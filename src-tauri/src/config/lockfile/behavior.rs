@@ -1,9 +1,10 @@
 use super::types::*;
+use crate::config::manifest::{MANIFEST_FILENAME, TOML_MANIFEST_FILENAME};
 use crate::error::{CogniaError, CogniaResult};
 use crate::platform::fs;
 use chrono::Utc;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 impl Default for Lockfile {
     fn default() -> Self {
@@ -20,6 +21,7 @@ pub fn new() -> Self {
             environments: HashMap::new(),
             packages: Vec::new(),
             dependency_graph_hash: None,
+            manifest_hash: None,
         }
     }
 
@@ -41,7 +43,16 @@ pub async fn load_from_dir(dir: &Path) -> CogniaResult<Option<Self>> {
         Ok(Some(lockfile))
     }
 
-    pub async fn save(&self, path: &Path) -> CogniaResult<()> {
+    /// Write the lockfile, recording the hash of the manifest it sits next
+    /// to so diagnostics can tell when that manifest changes afterwards.
+    pub async fn save(&mut self, path: &Path) -> CogniaResult<()> {
+        if let Some(dir) = path.parent() {
+            if let Some(manifest) = Self::manifest_in(dir).await {
+                let content = fs::read_file_string(&manifest).await?;
+                self.record_manifest(&content);
+            }
+        }
+
         let header = "# This file is auto-generated by CogniaLauncher. Do not edit manually.\n\n";
         let content = serde_yaml::to_string(self)
             .map_err(|e| CogniaError::Config(format!("Failed to serialize lockfile: {}", e)))?;
@@ -103,6 +114,30 @@ pub fn update_hash(&mut self) {
 
         self.dependency_graph_hash = Some(hex::encode(hasher.finalize()));
     }
+
+    /// Hash of manifest content, ignoring line-ending differences so a
+    /// checkout with CRLF endings does not read as drift.
+    pub fn manifest_content_hash(content: &str) -> String {
+        use sha2::{Digest, Sha256};
+
+        hex::encode(Sha256::digest(content.replace("\r\n", "\n").as_bytes()))
+    }
+
+    /// The manifest a lockfile in `dir` locks: `CogniaLauncher.yaml`, or
+    /// `cognia.toml` for projects that only have that.
+    pub async fn manifest_in(dir: &Path) -> Option<PathBuf> {
+        for name in [MANIFEST_FILENAME, TOML_MANIFEST_FILENAME] {
+            let candidate = dir.join(name);
+            if fs::exists(&candidate).await {
+                return Some(candidate);
+            }
+        }
+        None
+    }
+
+    pub fn record_manifest(&mut self, content: &str) {
+        self.manifest_hash = Some(Self::manifest_content_hash(content));
+    }
 }
 
 impl LockedPackage {
@@ -162,6 +162,34 @@ fn test_update_hash_empty() {
     assert_eq!(hash.len(), 64);
 }
 
+#[test]
+fn test_manifest_content_hash_ignores_line_endings() {
+    assert_eq!(
+        Lockfile::manifest_content_hash("project:\n  name: demo\n"),
+        Lockfile::manifest_content_hash("project:\r\n  name: demo\r\n")
+    );
+    assert_ne!(
+        Lockfile::manifest_content_hash("project:\n  name: demo\n"),
+        Lockfile::manifest_content_hash("project:\n  name: other\n")
+    );
+}
+
+#[test]
+fn test_record_manifest_round_trips() {
+    let mut lockfile = Lockfile::new();
+    assert!(!serde_yaml::to_string(&lockfile)
+        .unwrap()
+        .contains("manifest_hash"));
+
+    lockfile.record_manifest("environments: {}\n");
+    let parsed: Lockfile =
+        serde_yaml::from_str(&serde_yaml::to_string(&lockfile).unwrap()).unwrap();
+    assert_eq!(
+        parsed.manifest_hash,
+        Some(Lockfile::manifest_content_hash("environments: {}\n"))
+    );
+}
+
 #[test]
 fn test_locked_package_new() {
     let pkg = LockedPackage::new("ripgrep", "14.0.3", "github");
@@ -294,3 +322,30 @@ fn test_serialize_deserialize_with_all_fields() {
     let art = p.artifacts.get("x86_64-linux").unwrap();
     assert_eq!(art.checksum.sha512, Some("ghi".to_string()));
 }
+
+#[tokio::test]
+async fn test_save_records_sibling_manifest_hash() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(LOCKFILE_FILENAME);
+
+    let mut lockfile = Lockfile::new();
+    lockfile.save(&path).await.unwrap();
+    assert!(Lockfile::load(&path).await.unwrap().manifest_hash.is_none());
+
+    let toml = "[environments]\nnode = \"20\"\n";
+    std::fs::write(dir.path().join("cognia.toml"), toml).unwrap();
+    lockfile.save(&path).await.unwrap();
+    assert_eq!(
+        Lockfile::load(&path).await.unwrap().manifest_hash,
+        Some(Lockfile::manifest_content_hash(toml))
+    );
+
+    // The YAML manifest owns the lockfile when both exist
+    let yaml = "environments:\n  node:\n    version: \"20\"\n";
+    std::fs::write(dir.path().join("CogniaLauncher.yaml"), yaml).unwrap();
+    lockfile.save(&path).await.unwrap();
+    assert_eq!(
+        Lockfile::load(&path).await.unwrap().manifest_hash,
+        Some(Lockfile::manifest_content_hash(yaml))
+    );
+}
@@ -14,6 +14,9 @@ pub struct Lockfile {
     #[serde(default)]
     pub packages: Vec<LockedPackage>,
     pub dependency_graph_hash: Option<String>,
+    /// SHA-256 of the manifest this lockfile was generated from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest_hash: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
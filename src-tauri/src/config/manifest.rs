@@ -1,6 +1,11 @@
 mod behavior;
+mod diagnostics;
+mod format;
+mod schema;
 mod types;
 
+pub use diagnostics::*;
+pub use format::*;
 pub use types::*;
 
 #[cfg(test)]
@@ -0,0 +1,1090 @@
+use super::schema::{root_shape, Field, Shape};
+use super::types::{Manifest, TomlManifest};
+use crate::config::lockfile::{Lockfile, LOCKFILE_FILENAME};
+use crate::core::environment::{candidate_provider_ids, provider_to_env_type};
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::fs;
+use crate::resolver::{Version, VersionConstraint};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ManifestFormat {
+    /// `cognia.toml`
+    Toml,
+    /// `CogniaLauncher.yaml`
+    Yaml,
+}
+
+impl ManifestFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "toml" => Some(Self::Toml),
+            "yaml" | "yml" => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+    Hint,
+}
+
+/// Span in the source file. Lines and columns are 1-based; the end column
+/// is exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextRange {
+    pub start_line: u32,
+    pub start_column: u32,
+    pub end_line: u32,
+    pub end_column: u32,
+}
+
+impl TextRange {
+    fn point(line: u32, column: u32) -> Self {
+        Self {
+            start_line: line,
+            start_column: column,
+            end_line: line,
+            end_column: column + 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestDiagnostic {
+    /// Stable machine-readable code, e.g. `unknown_key` or `lockfile_drift`
+    pub code: String,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+    /// Key path such as `environments.node.version` or `packages[1].name`
+    pub path: Option<String>,
+    pub range: TextRange,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestDiagnostics {
+    pub path: String,
+    pub format: ManifestFormat,
+    pub diagnostics: Vec<ManifestDiagnostic>,
+    /// Lockfile the manifest was compared against, if one exists
+    pub lockfile_path: Option<String>,
+}
+
+/// Provider ids that provider references are checked against. Provider
+/// checks are skipped when `known_providers` is empty.
+#[derive(Debug, Clone, Default)]
+pub struct DiagnosticContext {
+    pub known_providers: HashSet<String>,
+    pub enabled_providers: HashSet<String>,
+}
+
+/// Reads the manifest at `path` and diagnoses it, comparing it against the
+/// sibling lockfile when one exists and was generated from this manifest.
+pub async fn diagnose_manifest_file(
+    path: &Path,
+    context: &DiagnosticContext,
+) -> CogniaResult<ManifestDiagnostics> {
+    let format = ManifestFormat::from_path(path).ok_or_else(|| {
+        CogniaError::Config(format!("Unsupported manifest file: {}", path.display()))
+    })?;
+    let content = fs::read_file_string(path).await?;
+
+    let mut lockfile = None;
+    let mut lockfile_error = None;
+    let mut lockfile_path = None;
+    if let Some(dir) = path.parent() {
+        let candidate = dir.join(LOCKFILE_FILENAME);
+        // With both manifests present the lockfile belongs to the YAML one
+        let owns_lockfile = Lockfile::manifest_in(dir)
+            .await
+            .is_some_and(|manifest| manifest.file_name() == path.file_name());
+        if owns_lockfile && fs::exists(&candidate).await {
+            match Lockfile::load(&candidate).await {
+                Ok(loaded) => lockfile = Some(loaded),
+                Err(e) => lockfile_error = Some(e.to_string()),
+            }
+            lockfile_path = Some(candidate.display().to_string());
+        }
+    }
+
+    let mut diagnostics = diagnose_manifest(&content, format, context, lockfile.as_ref());
+    if let Some(message) = lockfile_error {
+        diagnostics.insert(
+            0,
+            ManifestDiagnostic {
+                code: "lockfile_parse_error".into(),
+                severity: DiagnosticSeverity::Warning,
+                message: format!("{} is unreadable: {}", LOCKFILE_FILENAME, message),
+                path: None,
+                range: TextRange::point(1, 1),
+            },
+        );
+    }
+
+    Ok(ManifestDiagnostics {
+        path: path.display().to_string(),
+        format,
+        diagnostics,
+        lockfile_path,
+    })
+}
+
+/// Diagnoses manifest `content`, returning diagnostics ordered by position.
+pub fn diagnose_manifest(
+    content: &str,
+    format: ManifestFormat,
+    context: &DiagnosticContext,
+    lockfile: Option<&Lockfile>,
+) -> Vec<ManifestDiagnostic> {
+    let root = match parse_manifest_value(content, format) {
+        Ok(root) => root,
+        Err(diagnostic) => return vec![diagnostic],
+    };
+
+    let index = SourceIndex::new(content, format);
+    let mut checker = Checker {
+        index: &index,
+        context,
+        diagnostics: Vec::new(),
+    };
+    checker.check_shape(&root, root_shape(format), "");
+
+    // The schema is traced from the manifest types but knows nothing about
+    // values, so still surface anything else the loader would reject.
+    let has_errors = checker
+        .diagnostics
+        .iter()
+        .any(|d| d.severity == DiagnosticSeverity::Error);
+    if !has_errors {
+        let error = match format {
+            ManifestFormat::Yaml => serde_yaml::from_str::<Manifest>(content)
+                .err()
+                .map(|e| e.to_string()),
+            ManifestFormat::Toml => toml::from_str::<TomlManifest>(content)
+                .err()
+                .map(|e| e.to_string()),
+        };
+        if let Some(e) = error {
+            checker.push(
+                "",
+                Target::Key,
+                "invalid_manifest",
+                DiagnosticSeverity::Error,
+                format!("Manifest cannot be loaded: {}", e),
+            );
+        }
+    }
+
+    checker.check_environments(&root);
+    checker.check_packages(&root);
+    if let Some(lockfile) = lockfile {
+        checker.check_lockfile(content, &root, lockfile);
+    }
+
+    let mut diagnostics = checker.diagnostics;
+    diagnostics.sort_by_key(|d| (d.range.start_line, d.range.start_column));
+    diagnostics
+}
+
+/// Parses a manifest into a format-neutral value, reporting syntax errors
+/// as a `parse_error` diagnostic.
+pub(super) fn parse_manifest_value(
+    content: &str,
+    format: ManifestFormat,
+) -> Result<Value, ManifestDiagnostic> {
+    let parse_error = |message: String, range: TextRange| ManifestDiagnostic {
+        code: "parse_error".into(),
+        severity: DiagnosticSeverity::Error,
+        message,
+        path: None,
+        range,
+    };
+
+    let value = match format {
+        ManifestFormat::Toml => {
+            let table = content.parse::<toml::Table>().map_err(|e| {
+                let range = e
+                    .span()
+                    .map(|span| {
+                        let (line, column) = position_at(content, span.start);
+                        TextRange::point(line, column)
+                    })
+                    .unwrap_or(TextRange::point(1, 1));
+                parse_error(e.message().trim().to_string(), range)
+            })?;
+            serde_json::to_value(table)
+        }
+        ManifestFormat::Yaml => {
+            let value = serde_yaml::from_str::<serde_yaml::Value>(content).map_err(|e| {
+                let range = e
+                    .location()
+                    .map(|at| TextRange::point(at.line() as u32, at.column() as u32))
+                    .unwrap_or(TextRange::point(1, 1));
+                parse_error(e.to_string(), range)
+            })?;
+            serde_json::to_value(value)
+        }
+    }
+    .map_err(|e| parse_error(e.to_string(), TextRange::point(1, 1)))?;
+
+    // An empty YAML document is an empty manifest.
+    Ok(match value {
+        Value::Null => Value::Object(Map::new()),
+        value => value,
+    })
+}
+
+fn position_at(content: &str, offset: usize) -> (u32, u32) {
+    let before = content.get(..offset).unwrap_or(content);
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+    (line as u32, column as u32)
+}
+
+#[derive(Clone, Copy)]
+enum Target {
+    Key,
+    Value,
+}
+
+struct Checker<'a> {
+    index: &'a SourceIndex,
+    context: &'a DiagnosticContext,
+    diagnostics: Vec<ManifestDiagnostic>,
+}
+
+impl Checker<'_> {
+    fn push(
+        &mut self,
+        path: &str,
+        target: Target,
+        code: &str,
+        severity: DiagnosticSeverity,
+        message: String,
+    ) {
+        self.diagnostics.push(ManifestDiagnostic {
+            code: code.to_string(),
+            severity,
+            message,
+            path: (!path.is_empty()).then(|| path.to_string()),
+            range: self.index.range(path, target),
+        });
+    }
+
+    fn check_shape(&mut self, value: &Value, shape: &Shape, path: &str) {
+        match (shape, value) {
+            (Shape::String, Value::String(_)) | (Shape::Bool, Value::Bool(_)) => {}
+            (Shape::StringOr(_), Value::String(_)) => {}
+            (Shape::List(item), Value::Array(items)) => {
+                for (i, entry) in items.iter().enumerate() {
+                    self.check_shape(entry, item, &format!("{}[{}]", path, i));
+                }
+            }
+            (Shape::Record(fields), Value::Object(map))
+            | (Shape::StringOr(fields), Value::Object(map)) => {
+                self.check_record(fields, map, path);
+            }
+            (Shape::Dict(inner), Value::Object(map)) => {
+                for (key, entry) in map {
+                    self.check_shape(entry, inner, &join_path(path, key));
+                }
+            }
+            _ => self.push(
+                path,
+                Target::Value,
+                "invalid_type",
+                DiagnosticSeverity::Error,
+                format!(
+                    "Expected {} for {}, found {}",
+                    shape.describe(),
+                    display_path(path),
+                    describe_value(value)
+                ),
+            ),
+        }
+    }
+
+    fn check_record(&mut self, fields: &[Field], map: &Map<String, Value>, path: &str) {
+        for (key, entry) in map {
+            let child = join_path(path, key);
+            match fields.iter().find(|f| f.name == key) {
+                // `key:` with no value loads as an absent optional string.
+                Some(field)
+                    if entry.is_null()
+                        && !field.required
+                        && matches!(field.shape, Shape::String) => {}
+                Some(field) => self.check_shape(entry, &field.shape, &child),
+                None => {
+                    let suggestion = closest_field(key, fields)
+                        .map(|name| format!("; did you mean `{}`?", name))
+                        .unwrap_or_default();
+                    self.push(
+                        &child,
+                        Target::Key,
+                        "unknown_key",
+                        DiagnosticSeverity::Warning,
+                        format!(
+                            "Unknown key `{}` in {}{}",
+                            key,
+                            display_path(path),
+                            suggestion
+                        ),
+                    );
+                }
+            }
+        }
+
+        for field in fields
+            .iter()
+            .filter(|f| f.required && !map.contains_key(f.name))
+        {
+            self.push(
+                path,
+                Target::Key,
+                "missing_field",
+                DiagnosticSeverity::Error,
+                format!(
+                    "Missing required key `{}` in {}",
+                    field.name,
+                    display_path(path)
+                ),
+            );
+        }
+    }
+
+    fn check_environments(&mut self, root: &Value) {
+        let mut tables = vec![("environments".to_string(), root.get("environments"))];
+        if let Some(Value::Object(platforms)) = root.get("platforms") {
+            for (platform, entry) in platforms {
+                tables.push((
+                    format!("platforms.{}.environments", platform),
+                    entry.get("environments"),
+                ));
+            }
+        }
+
+        for (base, table) in tables {
+            let Some(Value::Object(environments)) = table else {
+                continue;
+            };
+            for (env_type, spec) in environments {
+                let env_path = join_path(&base, env_type);
+                self.check_env_type(env_type, &env_path);
+                if let Some((version, version_path)) = environment_version(spec, &env_path) {
+                    self.check_constraint(version, &version_path);
+                }
+                if let Some(provider) = spec.get("provider").and_then(Value::as_str) {
+                    self.check_provider(provider, &format!("{}.provider", env_path));
+                }
+            }
+        }
+    }
+
+    fn check_env_type(&mut self, env_type: &str, path: &str) {
+        let normalized = provider_to_env_type(env_type);
+        if candidate_provider_ids(&normalized).is_empty()
+            && !self.context.known_providers.contains(env_type)
+        {
+            self.push(
+                path,
+                Target::Key,
+                "unknown_env_type",
+                DiagnosticSeverity::Warning,
+                format!("Unknown environment type `{}`", env_type),
+            );
+        }
+    }
+
+    fn check_packages(&mut self, root: &Value) {
+        let mut lists = vec![("packages".to_string(), root.get("packages"))];
+        for section in ["platforms", "profiles"] {
+            if let Some(Value::Object(entries)) = root.get(section) {
+                for (name, entry) in entries {
+                    lists.push((
+                        format!("{}.{}.packages", section, name),
+                        entry.get("packages"),
+                    ));
+                }
+            }
+        }
+
+        for (base, list) in lists {
+            let Some(Value::Array(items)) = list else {
+                continue;
+            };
+            for (i, item) in items.iter().enumerate() {
+                let item_path = format!("{}[{}]", base, i);
+                if let Some(version) = item.get("version").and_then(Value::as_str) {
+                    self.check_constraint(version, &format!("{}.version", item_path));
+                }
+                if let Some(provider) = item.get("provider").and_then(Value::as_str) {
+                    self.check_provider(provider, &format!("{}.provider", item_path));
+                }
+            }
+        }
+    }
+
+    fn check_constraint(&mut self, constraint: &str, path: &str) {
+        if is_version_alias(constraint) {
+            return;
+        }
+        if constraint.parse::<VersionConstraint>().is_err() {
+            self.push(
+                path,
+                Target::Value,
+                "invalid_version_constraint",
+                DiagnosticSeverity::Error,
+                format!("`{}` is not a valid version constraint", constraint),
+            );
+        }
+    }
+
+    fn check_provider(&mut self, provider: &str, path: &str) {
+        if self.context.known_providers.is_empty() {
+            return;
+        }
+        if !self.context.known_providers.contains(provider) {
+            self.push(
+                path,
+                Target::Value,
+                "unknown_provider",
+                DiagnosticSeverity::Warning,
+                format!("Provider `{}` is not registered", provider),
+            );
+        } else if !self.context.enabled_providers.contains(provider) {
+            self.push(
+                path,
+                Target::Value,
+                "provider_not_enabled",
+                DiagnosticSeverity::Warning,
+                format!(
+                    "Provider `{}` is disabled; enable it before installing from this manifest",
+                    provider
+                ),
+            );
+        }
+    }
+
+    fn check_lockfile(&mut self, content: &str, root: &Value, lockfile: &Lockfile) {
+        match lockfile.manifest_hash.as_deref() {
+            Some(hash) if hash != Lockfile::manifest_content_hash(content) => self.push(
+                "",
+                Target::Key,
+                "lockfile_drift",
+                DiagnosticSeverity::Warning,
+                format!(
+                    "Manifest has changed since {} was generated",
+                    LOCKFILE_FILENAME
+                ),
+            ),
+            Some(_) => {}
+            None => self.push(
+                "",
+                Target::Key,
+                "lockfile_unhashed",
+                DiagnosticSeverity::Hint,
+                format!(
+                    "{} does not record a manifest hash; only locked versions are checked",
+                    LOCKFILE_FILENAME
+                ),
+            ),
+        }
+
+        let empty = Map::new();
+        let environments = match root.get("environments") {
+            Some(Value::Object(environments)) => environments,
+            _ => &empty,
+        };
+        for (env_type, spec) in environments {
+            let env_path = join_path("environments", env_type);
+            let Some(locked) = lockfile.environments.get(env_type) else {
+                self.push(
+                    &env_path,
+                    Target::Key,
+                    "lockfile_env_missing",
+                    DiagnosticSeverity::Warning,
+                    format!("`{}` is not pinned in {}", env_type, LOCKFILE_FILENAME),
+                );
+                continue;
+            };
+            if let Some((constraint, version_path)) = environment_version(spec, &env_path) {
+                if !constraint_accepts(constraint, &locked.version) {
+                    self.push(
+                        &version_path,
+                        Target::Value,
+                        "lockfile_env_mismatch",
+                        DiagnosticSeverity::Warning,
+                        format!(
+                            "Locked {} {} does not satisfy `{}`",
+                            env_type, locked.version, constraint
+                        ),
+                    );
+                }
+            }
+        }
+
+        let mut stale: Vec<&String> = lockfile
+            .environments
+            .keys()
+            .filter(|env_type| !environments.contains_key(env_type.as_str()))
+            .collect();
+        stale.sort();
+        for env_type in stale {
+            self.push(
+                "",
+                Target::Key,
+                "lockfile_env_stale",
+                DiagnosticSeverity::Hint,
+                format!(
+                    "{} pins `{}`, which the manifest no longer declares",
+                    LOCKFILE_FILENAME, env_type
+                ),
+            );
+        }
+    }
+}
+
+/// Version of an environment entry: a bare string in `cognia.toml`, or the
+/// `version` key of an environment spec.
+fn environment_version<'v>(spec: &'v Value, env_path: &str) -> Option<(&'v str, String)> {
+    match spec {
+        Value::String(version) => Some((version.as_str(), env_path.to_string())),
+        Value::Object(map) => map
+            .get("version")
+            .and_then(Value::as_str)
+            .map(|version| (version, format!("{}.version", env_path))),
+        _ => None,
+    }
+}
+
+/// Named versions such as `stable`, `lts/iron` or `nightly` are resolved by
+/// providers and are not constraints.
+fn is_version_alias(raw: &str) -> bool {
+    let raw = raw.trim();
+    let mut chars = raw.chars();
+    match chars.next() {
+        Some('v' | 'V') => !chars.next().is_some_and(|c| c.is_ascii_digit()),
+        Some(c) => c.is_ascii_alphabetic() && raw != "latest",
+        None => false,
+    }
+}
+
+fn constraint_accepts(constraint: &str, locked: &str) -> bool {
+    let constraint = constraint.trim();
+    if is_version_alias(constraint) {
+        return true;
+    }
+
+    // A partial version like `20` or `3.12` pins a release line.
+    let bare = constraint.trim_start_matches(['v', 'V']);
+    if !bare.is_empty()
+        && bare.chars().all(|c| c.is_ascii_digit() || c == '.')
+        && bare.split('.').count() < 3
+    {
+        let locked = locked.trim().trim_start_matches(['v', 'V']);
+        return locked == bare || locked.starts_with(&format!("{}.", bare));
+    }
+
+    match (
+        constraint.parse::<VersionConstraint>(),
+        locked.parse::<Version>(),
+    ) {
+        (Ok(constraint), Ok(version)) => constraint.matches(&version),
+        _ => true,
+    }
+}
+
+fn closest_field(key: &str, fields: &[Field]) -> Option<&'static str> {
+    fields
+        .iter()
+        .map(|f| (edit_distance(key, f.name), f.name))
+        .filter(|(distance, _)| *distance <= 2 && *distance < key.len())
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, name)| name)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+fn join_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", parent, key)
+    }
+}
+
+fn parent_path(path: &str) -> Option<&str> {
+    if path.is_empty() {
+        return None;
+    }
+    if path.ends_with(']') {
+        if let Some(i) = path.rfind('[') {
+            return Some(&path[..i]);
+        }
+    }
+    Some(path.rfind('.').map_or("", |i| &path[..i]))
+}
+
+fn display_path(path: &str) -> String {
+    if path.is_empty() {
+        "the manifest root".to_string()
+    } else {
+        format!("`{}`", path)
+    }
+}
+
+fn describe_value(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "nothing",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "a list",
+        Value::Object(_) => "a table",
+    }
+}
+
+struct KeySpan {
+    key: TextRange,
+    value: Option<TextRange>,
+}
+
+/// Line-based map from key paths to their source spans. It does not parse
+/// the file; it only needs to be right for files that already parsed.
+struct SourceIndex {
+    entries: HashMap<String, KeySpan>,
+    first_line: TextRange,
+}
+
+impl SourceIndex {
+    fn new(content: &str, format: ManifestFormat) -> Self {
+        let mut entries = HashMap::new();
+        match format {
+            ManifestFormat::Toml => index_toml(content, &mut entries),
+            ManifestFormat::Yaml => index_yaml(content, &mut entries),
+        }
+
+        let first = content.lines().next().unwrap_or("");
+        Self {
+            entries,
+            first_line: line_range(0, first, 0, first.len()),
+        }
+    }
+
+    /// Span for `path`, falling back to the closest indexed ancestor.
+    fn range(&self, path: &str, target: Target) -> TextRange {
+        if let Some(span) = self.entries.get(path) {
+            return match target {
+                Target::Key => span.key,
+                Target::Value => span.value.unwrap_or(span.key),
+            };
+        }
+        let mut current = parent_path(path);
+        while let Some(ancestor) = current {
+            if let Some(span) = self.entries.get(ancestor) {
+                return span.key;
+            }
+            current = parent_path(ancestor);
+        }
+        self.first_line
+    }
+}
+
+fn line_range(line_no: usize, line: &str, start: usize, end: usize) -> TextRange {
+    let column = |offset: usize| line.get(..offset).unwrap_or(line).chars().count() as u32 + 1;
+    TextRange {
+        start_line: line_no as u32 + 1,
+        start_column: column(start),
+        end_line: line_no as u32 + 1,
+        end_column: column(end).max(column(start) + 1),
+    }
+}
+
+/// Characters of `s` outside quoted strings, with their byte offsets.
+pub(super) fn unquoted_chars(s: &str) -> impl Iterator<Item = (usize, char)> + '_ {
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    s.char_indices().filter(move |&(_, c)| {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' && q == '"' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            false
+        } else if c == '"' || c == '\'' {
+            quote = Some(c);
+            false
+        } else {
+            true
+        }
+    })
+}
+
+fn strip_toml_comment(s: &str) -> &str {
+    match unquoted_chars(s).find(|&(_, c)| c == '#') {
+        Some((i, _)) => &s[..i],
+        None => s,
+    }
+}
+
+/// Strips a trailing ` # comment`. Quotes only matter at the start of a
+/// YAML scalar, so apostrophes in plain text are left alone.
+pub(super) fn strip_yaml_comment(s: &str) -> &str {
+    if s.starts_with('#') {
+        return "";
+    }
+    let from = match s.chars().next() {
+        Some(q @ ('"' | '\'')) => s[1..].find(q).map_or(0, |i| i + 2),
+        _ => 0,
+    };
+    let comment = s[from..]
+        .char_indices()
+        .find(|&(i, c)| c == '#' && s[..from + i].ends_with(char::is_whitespace));
+    match comment {
+        Some((i, _)) => &s[..from + i],
+        None => s,
+    }
+}
+
+/// Splits a YAML `key: value` line, returning the key, the byte offset
+/// where the key text ends and the offset just past the colon.
+pub(super) fn split_yaml_key(rest: &str) -> Option<(String, usize, usize)> {
+    let first = rest.chars().next()?;
+    if matches!(first, '[' | '{' | '#' | '|' | '>' | '&' | '*' | '!') {
+        return None;
+    }
+
+    if first == '"' || first == '\'' {
+        let close = rest[1..].find(first)? + 1;
+        let after = &rest[close + 1..];
+        let colon = close + 1 + (after.len() - after.trim_start().len());
+        if !rest[colon..].starts_with(':') {
+            return None;
+        }
+        return Some((rest[1..close].to_string(), close + 1, colon + 1));
+    }
+
+    let (colon, _) = rest.char_indices().find(|&(i, c)| {
+        c == ':' && !rest[i + 1..].starts_with(|next: char| !next.is_whitespace())
+    })?;
+    if rest[..colon].contains(" #") {
+        return None;
+    }
+    let key = rest[..colon].trim_end();
+    Some((key.to_string(), key.len(), colon + 1))
+}
+
+pub(super) fn is_yaml_item(trimmed: &str) -> bool {
+    trimmed == "-" || trimmed.starts_with("- ")
+}
+
+fn index_yaml(content: &str, entries: &mut HashMap<String, KeySpan>) {
+    struct Frame {
+        indent: usize,
+        path: String,
+        item: bool,
+    }
+
+    let mut frames: Vec<Frame> = Vec::new();
+    let mut item_counts: HashMap<String, usize> = HashMap::new();
+    // Indent of the key that owns an open `|` or `>` block scalar
+    let mut block_scalar: Option<usize> = None;
+
+    for (line_no, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        let mut indent = line.len() - trimmed.len();
+        if let Some(owner) = block_scalar {
+            if trimmed.is_empty() || indent > owner {
+                continue;
+            }
+            block_scalar = None;
+        }
+        if trimmed.is_empty()
+            || trimmed.starts_with('#')
+            || trimmed.starts_with("---")
+            || trimmed.starts_with("...")
+        {
+            continue;
+        }
+
+        let mut rest = trimmed;
+        if is_yaml_item(trimmed) {
+            while frames
+                .last()
+                .is_some_and(|f| f.indent > indent || (f.indent == indent && f.item))
+            {
+                frames.pop();
+            }
+            let parent = frames.last().map(|f| f.path.clone()).unwrap_or_default();
+            let count = item_counts.entry(parent.clone()).or_insert(0);
+            let item_path = format!("{}[{}]", parent, count);
+            *count += 1;
+
+            let after_dash = trimmed[1..].trim_start();
+            let content_indent = line.len() - after_dash.len();
+            let dash = line_range(line_no, line, indent, indent + 1);
+            frames.push(Frame {
+                indent,
+                path: item_path.clone(),
+                item: true,
+            });
+
+            if split_yaml_key(after_dash).is_none() {
+                let scalar = strip_yaml_comment(after_dash).trim_end();
+                let value = (!scalar.is_empty()).then(|| {
+                    line_range(line_no, line, content_indent, content_indent + scalar.len())
+                });
+                entries.insert(item_path, KeySpan { key: dash, value });
+                continue;
+            }
+            entries.insert(
+                item_path,
+                KeySpan {
+                    key: dash,
+                    value: None,
+                },
+            );
+            indent = content_indent;
+            rest = after_dash;
+        } else {
+            while frames.last().is_some_and(|f| f.indent >= indent) {
+                frames.pop();
+            }
+        }
+
+        let Some((key, key_end, value_start)) = split_yaml_key(rest) else {
+            continue;
+        };
+        let parent = frames.last().map(|f| f.path.as_str()).unwrap_or("");
+        let path = join_path(parent, &key);
+        let key_start = line.len() - rest.len();
+
+        let raw_value = rest[value_start..].trim_start();
+        let value_offset = line.len() - raw_value.len();
+        let value_text = strip_yaml_comment(raw_value).trim_end();
+        let value = if value_text.is_empty() {
+            None
+        } else if value_text.starts_with('|') || value_text.starts_with('>') {
+            block_scalar = Some(indent);
+            None
+        } else {
+            Some(line_range(
+                line_no,
+                line,
+                value_offset,
+                value_offset + value_text.len(),
+            ))
+        };
+
+        entries.insert(
+            path.clone(),
+            KeySpan {
+                key: line_range(line_no, line, key_start, key_start + key_end),
+                value,
+            },
+        );
+        frames.push(Frame {
+            indent,
+            path,
+            item: false,
+        });
+    }
+}
+
+pub(super) fn normalize_toml_key(raw: &str) -> String {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    for (i, c) in unquoted_chars(raw) {
+        if c == '.' {
+            parts.push(&raw[start..i]);
+            start = i + 1;
+        }
+    }
+    parts.push(&raw[start..]);
+    parts
+        .iter()
+        .map(|part| part.trim().trim_matches(|c| c == '"' || c == '\''))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn index_toml(content: &str, entries: &mut HashMap<String, KeySpan>) {
+    let mut table = String::new();
+    let mut array_tables: HashMap<String, usize> = HashMap::new();
+    // Path and next item index of an array value spanning several lines
+    let mut open_array: Option<(String, usize)> = None;
+
+    for (line_no, line) in content.lines().enumerate() {
+        if let Some((path, next)) = open_array.as_mut() {
+            if index_toml_array(line_no, line, 0, path, next, entries) {
+                open_array = None;
+            }
+            continue;
+        }
+
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if let Some(header) = trimmed.strip_prefix("[[") {
+            let Some(end) = header.find("]]") else {
+                continue;
+            };
+            let name = normalize_toml_key(&header[..end]);
+            let count = array_tables.entry(name.clone()).or_insert(0);
+            table = format!("{}[{}]", name, count);
+            *count += 1;
+            entries.insert(
+                table.clone(),
+                KeySpan {
+                    key: line_range(line_no, line, indent, indent + end + 4),
+                    value: None,
+                },
+            );
+            continue;
+        }
+        if let Some(header) = trimmed.strip_prefix('[') {
+            let Some(end) = header.find(']') else {
+                continue;
+            };
+            table = normalize_toml_key(&header[..end]);
+            entries.insert(
+                table.clone(),
+                KeySpan {
+                    key: line_range(line_no, line, indent, indent + end + 2),
+                    value: None,
+                },
+            );
+            continue;
+        }
+
+        let Some((eq, _)) = unquoted_chars(trimmed).find(|&(_, c)| c == '=') else {
+            continue;
+        };
+        let raw_key = trimmed[..eq].trim_end();
+        let path = join_path(&table, &normalize_toml_key(raw_key));
+        let raw_value = trimmed[eq + 1..].trim_start();
+        let value_offset = line.len() - raw_value.len();
+        let value_text = strip_toml_comment(raw_value).trim_end();
+
+        entries.insert(
+            path.clone(),
+            KeySpan {
+                key: line_range(line_no, line, indent, indent + raw_key.len()),
+                value: Some(line_range(
+                    line_no,
+                    line,
+                    value_offset,
+                    value_offset + value_text.len(),
+                )),
+            },
+        );
+
+        if value_text.starts_with('[') {
+            let mut next = 0;
+            if !index_toml_array(line_no, line, value_offset + 1, &path, &mut next, entries) {
+                open_array = Some((path, next));
+            }
+        }
+    }
+}
+
+/// Indexes the items of an array value from byte `start` of `line`.
+/// Returns `true` once the array's closing bracket is reached.
+fn index_toml_array(
+    line_no: usize,
+    line: &str,
+    start: usize,
+    path: &str,
+    next: &mut usize,
+    entries: &mut HashMap<String, KeySpan>,
+) -> bool {
+    let mut depth = 0usize;
+    let mut item_start: Option<usize> = None;
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+
+    let mut close_item = |item_start: &mut Option<usize>, end: usize| {
+        if let Some(begin) = item_start.take() {
+            let text = line[begin..end].trim_end();
+            entries.insert(
+                format!("{}[{}]", path, next),
+                KeySpan {
+                    key: line_range(line_no, line, begin, begin + text.len()),
+                    value: None,
+                },
+            );
+            *next += 1;
+        }
+    };
+
+    for (i, c) in line[start..].char_indices() {
+        let at = start + i;
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' && q == '"' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => {
+                quote = Some(c);
+                item_start.get_or_insert(at);
+            }
+            '#' => break,
+            '[' | '{' => {
+                depth += 1;
+                item_start.get_or_insert(at);
+            }
+            ']' | '}' if depth > 0 => depth -= 1,
+            ']' => {
+                close_item(&mut item_start, at);
+                return true;
+            }
+            ',' if depth == 0 => close_item(&mut item_start, at),
+            c if c.is_whitespace() => {}
+            _ => {
+                item_start.get_or_insert(at);
+            }
+        }
+    }
+
+    if depth == 0 && quote.is_none() {
+        let end = strip_toml_comment(line).trim_end().len().max(start);
+        close_item(&mut item_start, end);
+    }
+    false
+}
@@ -0,0 +1,356 @@
+use super::diagnostics::{
+    is_yaml_item, normalize_toml_key, parse_manifest_value, split_yaml_key, unquoted_chars,
+    ManifestFormat,
+};
+use super::schema::{root_shape, Shape};
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::fs;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestFormatResult {
+    pub path: String,
+    pub changed: bool,
+    pub content: String,
+}
+
+/// Formats the manifest at `path` in place, writing only when the
+/// canonical form differs.
+pub async fn format_manifest_file(path: &Path) -> CogniaResult<ManifestFormatResult> {
+    let format = ManifestFormat::from_path(path).ok_or_else(|| {
+        CogniaError::Config(format!("Unsupported manifest file: {}", path.display()))
+    })?;
+    let content = fs::read_file_string(path).await?;
+    let formatted = format_manifest(&content, format)?;
+
+    let changed = formatted != content;
+    if changed {
+        fs::write_file_atomic(path, formatted.as_bytes()).await?;
+    }
+
+    Ok(ManifestFormatResult {
+        path: path.display().to_string(),
+        changed,
+        content: formatted,
+    })
+}
+
+/// Rewrites manifest `content` with keys in canonical order. Comments
+/// directly above a key move with it, free-form tables such as
+/// `environments` are sorted alphabetically, and list items keep their order.
+pub fn format_manifest(content: &str, format: ManifestFormat) -> CogniaResult<String> {
+    let original = parse_manifest_value(content, format).map_err(|d| {
+        CogniaError::Parse(format!(
+            "Cannot format a manifest with syntax errors: {}",
+            d.message
+        ))
+    })?;
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut out = Vec::with_capacity(lines.len());
+    match format {
+        ManifestFormat::Toml => format_toml(&lines, &mut out),
+        ManifestFormat::Yaml => format_yaml_mapping(&lines, Some(root_shape(format)), &mut out),
+    }
+
+    let newline = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut formatted = out.join(newline);
+    if content.ends_with('\n') {
+        formatted.push_str(newline);
+    }
+
+    // Reordering works on lines, so refuse anything that reads back differently.
+    if parse_manifest_value(&formatted, format).ok().as_ref() != Some(&original) {
+        return Err(CogniaError::Internal(
+            "Canonical ordering would change the manifest's meaning; file left unchanged"
+                .to_string(),
+        ));
+    }
+    Ok(formatted)
+}
+
+/// A key and the lines that belong to it: `lines[start..head]` are comments
+/// directly above, `lines[head]` is the key itself, `lines[head + 1..end]`
+/// its nested or continuation lines, and `lines[end..gap_end]` the blank
+/// lines that follow.
+struct Block {
+    start: usize,
+    head: usize,
+    end: usize,
+    gap_end: usize,
+}
+
+/// Splits `lines` at `heads`, pulling lines above each head into its block
+/// while `attaches` holds. Returns where the lead-in before the first block
+/// ends, along with the blocks.
+fn split_blocks(
+    lines: &[&str],
+    heads: &[usize],
+    attaches: impl Fn(&str) -> bool,
+) -> (usize, Vec<Block>) {
+    let mut starts = Vec::with_capacity(heads.len());
+    for (k, &head) in heads.iter().enumerate() {
+        let floor = if k == 0 { 0 } else { heads[k - 1] + 1 };
+        let mut start = head;
+        while start > floor && attaches(lines[start - 1]) {
+            start -= 1;
+        }
+        starts.push(start);
+    }
+
+    let blocks = heads
+        .iter()
+        .enumerate()
+        .map(|(k, &head)| {
+            let gap_end = starts.get(k + 1).copied().unwrap_or(lines.len());
+            let mut end = gap_end;
+            while end > head + 1 && lines[end - 1].trim().is_empty() {
+                end -= 1;
+            }
+            Block {
+                start: starts[k],
+                head,
+                end,
+                gap_end,
+            }
+        })
+        .collect();
+
+    (starts.first().copied().unwrap_or(lines.len()), blocks)
+}
+
+/// Emits `blocks` in rank order. Blank-line gaps stay where they were, so
+/// the spacing of the file survives reordering.
+fn emit_sorted<'a>(
+    lines: &[&'a str],
+    blocks: &[Block],
+    rank: impl Fn(&Block) -> (usize, String),
+    out: &mut Vec<&'a str>,
+    mut emit: impl FnMut(&Block, &mut Vec<&'a str>),
+) {
+    let mut order: Vec<usize> = (0..blocks.len()).collect();
+    order.sort_by_cached_key(|&i| rank(&blocks[i]));
+
+    for (position, &i) in order.iter().enumerate() {
+        emit(&blocks[i], out);
+        let slot = &blocks[position];
+        out.extend_from_slice(&lines[slot.end..slot.gap_end]);
+    }
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+fn format_yaml_mapping<'a>(lines: &[&'a str], shape: Option<&Shape>, out: &mut Vec<&'a str>) {
+    let significant = |line: &str| {
+        let trimmed = line.trim_start();
+        !trimmed.is_empty() && !trimmed.starts_with('#')
+    };
+    let Some(first) = lines
+        .iter()
+        .position(|line| significant(line) && !line.starts_with("---"))
+    else {
+        out.extend_from_slice(lines);
+        return;
+    };
+    let indent = indent_of(lines[first]);
+
+    let mut heads = Vec::new();
+    for (i, line) in lines.iter().enumerate().skip(first) {
+        if !significant(line) || indent_of(line) > indent {
+            continue;
+        }
+        let trimmed = line.trim_start();
+        if is_yaml_item(trimmed) && i > first {
+            // A compact list belonging to the previous key
+            continue;
+        }
+        if indent_of(line) < indent || is_yaml_item(trimmed) || split_yaml_key(trimmed).is_none() {
+            // Sequences, documents and flow syntax are left as written.
+            out.extend_from_slice(lines);
+            return;
+        }
+        heads.push(i);
+    }
+
+    let key_of = |block: &Block| {
+        split_yaml_key(lines[block.head].trim_start())
+            .map(|(key, _, _)| key)
+            .unwrap_or_default()
+    };
+    let (lead_end, blocks) = split_blocks(lines, &heads, |line| {
+        line.trim_start().starts_with('#') && indent_of(line) == indent
+    });
+    out.extend_from_slice(&lines[..lead_end]);
+
+    emit_sorted(
+        lines,
+        &blocks,
+        |block| {
+            shape
+                .map(|s| s.key_rank(&key_of(block)))
+                .unwrap_or_default()
+        },
+        out,
+        |block, out| {
+            out.extend_from_slice(&lines[block.start..=block.head]);
+            let children = &lines[block.head + 1..block.end];
+            match shape.and_then(|s| s.field(&key_of(block))) {
+                Some(child) if matches!(child, Shape::Record(_) | Shape::Dict(_)) => {
+                    format_yaml_mapping(children, Some(child), out)
+                }
+                _ => out.extend_from_slice(children),
+            }
+        },
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TomlLine {
+    Blank,
+    Comment,
+    Header,
+    Entry,
+    /// Inside a multi-line array or string
+    Continuation,
+}
+
+fn classify_toml(lines: &[&str]) -> Vec<TomlLine> {
+    let mut kinds = Vec::with_capacity(lines.len());
+    let mut depth = 0i32;
+    let mut open_string: Option<&str> = None;
+
+    for line in lines {
+        if let Some(delimiter) = open_string {
+            if line.matches(delimiter).count() % 2 == 1 {
+                open_string = None;
+            }
+            kinds.push(TomlLine::Continuation);
+            continue;
+        }
+        if depth > 0 {
+            depth += bracket_delta(line);
+            kinds.push(TomlLine::Continuation);
+            continue;
+        }
+
+        let trimmed = line.trim_start();
+        let kind = if trimmed.is_empty() {
+            TomlLine::Blank
+        } else if trimmed.starts_with('#') {
+            TomlLine::Comment
+        } else if trimmed.starts_with('[') {
+            TomlLine::Header
+        } else {
+            if let Some((eq, _)) = unquoted_chars(trimmed).find(|&(_, c)| c == '=') {
+                let value = &trimmed[eq + 1..];
+                open_string = ["\"\"\"", "'''"]
+                    .into_iter()
+                    .find(|delimiter| value.matches(delimiter).count() % 2 == 1);
+                if open_string.is_none() {
+                    depth = bracket_delta(value);
+                }
+            }
+            TomlLine::Entry
+        };
+        kinds.push(kind);
+    }
+    kinds
+}
+
+fn bracket_delta(s: &str) -> i32 {
+    let mut delta = 0;
+    for (_, c) in unquoted_chars(s) {
+        match c {
+            '#' => break,
+            '[' | '{' => delta += 1,
+            ']' | '}' => delta -= 1,
+            _ => {}
+        }
+    }
+    delta
+}
+
+fn is_toml_comment(line: &str) -> bool {
+    line.trim_start().starts_with('#')
+}
+
+fn toml_table_name(header: &str) -> (String, bool) {
+    let trimmed = header.trim();
+    let (inner, is_array) = match trimmed.strip_prefix("[[") {
+        Some(rest) => (rest.split("]]").next().unwrap_or(""), true),
+        None => (trimmed[1..].split(']').next().unwrap_or(""), false),
+    };
+    (normalize_toml_key(inner), is_array)
+}
+
+fn format_toml<'a>(lines: &[&'a str], out: &mut Vec<&'a str>) {
+    let kinds = classify_toml(lines);
+    let headers: Vec<usize> = (0..lines.len())
+        .filter(|&i| kinds[i] == TomlLine::Header)
+        .collect();
+    let root = root_shape(ManifestFormat::Toml);
+
+    // Root keys must stay above the first table, so they are sorted in place.
+    let (root_end, tables) = split_blocks(lines, &headers, is_toml_comment);
+    format_toml_entries(&lines[..root_end], &kinds[..root_end], Some(root), out);
+
+    let table_rank = |table: &Block| {
+        let (name, _) = toml_table_name(lines[table.head]);
+        let first = name.split('.').next().unwrap_or("");
+        root.key_rank(first)
+    };
+    emit_sorted(lines, &tables, table_rank, out, |table, out| {
+        out.extend_from_slice(&lines[table.start..=table.head]);
+        let (name, is_array) = toml_table_name(lines[table.head]);
+        let mut shape = Some(root);
+        for segment in name.split('.') {
+            shape = shape.and_then(|s| s.field(segment));
+        }
+        if is_array {
+            shape = shape.and_then(|s| s.item());
+        }
+        let body = table.head + 1..table.end;
+        format_toml_entries(&lines[body.clone()], &kinds[body], shape, out);
+    });
+}
+
+fn format_toml_entries<'a>(
+    lines: &[&'a str],
+    kinds: &[TomlLine],
+    shape: Option<&Shape>,
+    out: &mut Vec<&'a str>,
+) {
+    let heads: Vec<usize> = (0..lines.len())
+        .filter(|&i| kinds[i] == TomlLine::Entry)
+        .collect();
+    let (lead_end, blocks) = split_blocks(lines, &heads, is_toml_comment);
+    out.extend_from_slice(&lines[..lead_end]);
+
+    let key_of = |block: &Block| {
+        let line = lines[block.head];
+        let key = unquoted_chars(line)
+            .find(|&(_, c)| c == '=')
+            .map(|(eq, _)| normalize_toml_key(&line[..eq]))
+            .unwrap_or_default();
+        key.split('.').next().unwrap_or("").to_string()
+    };
+    emit_sorted(
+        lines,
+        &blocks,
+        |block| {
+            shape
+                .map(|s| s.key_rank(&key_of(block)))
+                .unwrap_or_default()
+        },
+        out,
+        |block, out| out.extend_from_slice(&lines[block.start..block.end]),
+    );
+}
@@ -0,0 +1,386 @@
+//! Expected shape of a manifest, shared by diagnostics and formatting.
+//!
+//! The shape is read off the manifest types themselves: they are
+//! deserialized from a tracer that records every field serde asks for, so
+//! the checks cannot drift from what the loader accepts.
+
+use super::diagnostics::ManifestFormat;
+use super::types::{DetailedPackageDep, Manifest, TomlManifest};
+use once_cell::sync::Lazy;
+use serde::de::{self, DeserializeOwned, IntoDeserializer, MapAccess, SeqAccess, Visitor};
+use serde::forward_to_deserialize_any;
+use std::collections::HashSet;
+use std::fmt;
+
+/// Expected shape of a manifest value.
+#[derive(Debug, Clone)]
+pub(super) enum Shape {
+    String,
+    Bool,
+    List(Box<Shape>),
+    /// Fixed keys, in declaration order, which is also the canonical order
+    Record(Vec<Field>),
+    /// Free-form keys, formatted alphabetically
+    Dict(Box<Shape>),
+    /// Either a bare string or a record, like package dependencies
+    StringOr(Vec<Field>),
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct Field {
+    pub name: &'static str,
+    pub shape: Shape,
+    pub required: bool,
+}
+
+static TOML_ROOT: Lazy<Shape> = Lazy::new(describe::<TomlManifest>);
+static YAML_ROOT: Lazy<Shape> = Lazy::new(describe::<Manifest>);
+
+/// Fields of the table form of a package dependency.
+static PACKAGE_FIELDS: Lazy<Vec<Field>> = Lazy::new(|| match describe::<DetailedPackageDep>() {
+    Shape::Record(fields) => fields,
+    _ => Vec::new(),
+});
+
+pub(super) fn root_shape(format: ManifestFormat) -> &'static Shape {
+    match format {
+        ManifestFormat::Toml => &TOML_ROOT,
+        ManifestFormat::Yaml => &YAML_ROOT,
+    }
+}
+
+impl Shape {
+    pub(super) fn describe(&self) -> &'static str {
+        match self {
+            Self::String => "a string",
+            Self::Bool => "a boolean",
+            Self::List(_) => "a list",
+            Self::Record(_) | Self::Dict(_) => "a table",
+            Self::StringOr(_) => "a string or a table",
+        }
+    }
+
+    /// Shape of the value stored under `key`, if this shape is a table.
+    pub(super) fn field(&self, key: &str) -> Option<&Shape> {
+        match self {
+            Self::Record(fields) | Self::StringOr(fields) => {
+                fields.iter().find(|f| f.name == key).map(|f| &f.shape)
+            }
+            Self::Dict(inner) => Some(inner),
+            _ => None,
+        }
+    }
+
+    /// Shape of each item, if this shape is a list.
+    pub(super) fn item(&self) -> Option<&Shape> {
+        match self {
+            Self::List(inner) => Some(inner),
+            _ => None,
+        }
+    }
+
+    /// Canonical sort key for `key` among the keys of this table. Unknown
+    /// keys sort after known ones and keep their relative order.
+    pub(super) fn key_rank(&self, key: &str) -> (usize, String) {
+        match self {
+            Self::Record(fields) | Self::StringOr(fields) => (
+                fields
+                    .iter()
+                    .position(|f| f.name == key)
+                    .unwrap_or(fields.len()),
+                String::new(),
+            ),
+            Self::Dict(_) => (0, key.to_string()),
+            _ => (0, String::new()),
+        }
+    }
+}
+
+/// Shape of `T`. A field is required when leaving it out makes serde report
+/// it missing, so `Option`s and `#[serde(default)]` fields are optional.
+fn describe<T: DeserializeOwned>() -> Shape {
+    let shape = trace::<T>(&Trace::default()).expect("manifest types are traceable");
+    let mut fields = Vec::new();
+    record_fields(&shape, "", &mut fields);
+
+    let mut required = HashSet::new();
+    for (path, name) in fields {
+        let probe = Trace {
+            omit: Some((path.clone(), name)),
+            required: HashSet::new(),
+        };
+        if matches!(trace::<T>(&probe), Err(TraceError::MissingField(missing)) if missing == name) {
+            required.insert(join(&path, name));
+        }
+    }
+
+    trace::<T>(&Trace {
+        omit: None,
+        required,
+    })
+    .expect("manifest types are traceable")
+}
+
+fn trace<T: DeserializeOwned>(trace: &Trace) -> Result<Shape, TraceError> {
+    let mut slot = None;
+    T::deserialize(Tracer {
+        trace,
+        path: String::new(),
+        slot: &mut slot,
+    })?;
+    slot.ok_or_else(|| TraceError::Other("nothing was deserialized".into()))
+}
+
+/// Every `(record path, field name)` pair in `shape`. Paths use `*` for
+/// dictionary values and `[]` for list items, as the tracer does.
+fn record_fields(shape: &Shape, path: &str, out: &mut Vec<(String, &'static str)>) {
+    match shape {
+        Shape::Record(fields) => {
+            for field in fields {
+                out.push((path.to_string(), field.name));
+                record_fields(&field.shape, &join(path, field.name), out);
+            }
+        }
+        Shape::List(item) => record_fields(item, &format!("{}[]", path), out),
+        Shape::Dict(inner) => record_fields(inner, &join(path, "*"), out),
+        Shape::String | Shape::Bool | Shape::StringOr(_) => {}
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+#[derive(Default)]
+struct Trace {
+    /// Record path and field to leave out, to see whether it is required
+    omit: Option<(String, &'static str)>,
+    /// `path.field` of every required field
+    required: HashSet<String>,
+}
+
+#[derive(Debug)]
+enum TraceError {
+    MissingField(&'static str),
+    Other(String),
+}
+
+impl fmt::Display for TraceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingField(field) => write!(f, "missing field `{}`", field),
+            Self::Other(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for TraceError {}
+
+impl de::Error for TraceError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::Other(msg.to_string())
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        Self::MissingField(field)
+    }
+}
+
+/// Deserializer that hands out placeholder values and records the shape
+/// serde asked for into `slot`. Options are always present, lists have one
+/// item and dictionaries one entry, so every nested type is visited.
+struct Tracer<'t, 's> {
+    trace: &'t Trace,
+    path: String,
+    slot: &'s mut Option<Shape>,
+}
+
+impl<'de, 't, 's> de::Deserializer<'de> for Tracer<'t, 's> {
+    type Error = TraceError;
+
+    /// Only untagged enums get here, and `PackageDep` is the one manifests
+    /// use: a package name or a package table.
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        *self.slot = Some(Shape::StringOr(PACKAGE_FIELDS.clone()));
+        visitor.visit_str("")
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        *self.slot = Some(Shape::Bool);
+        visitor.visit_bool(false)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        *self.slot = Some(Shape::String);
+        visitor.visit_str("")
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        let mut item = None;
+        let value = visitor.visit_seq(OneItem {
+            item: Some(Tracer {
+                trace: self.trace,
+                path: format!("{}[]", self.path),
+                slot: &mut item,
+            }),
+        })?;
+        *self.slot = Some(Shape::List(Box::new(item.unwrap_or(Shape::String))));
+        Ok(value)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        let mut entry = None;
+        let value = visitor.visit_map(OneEntry {
+            key_taken: false,
+            value: Some(Tracer {
+                trace: self.trace,
+                path: join(&self.path, "*"),
+                slot: &mut entry,
+            }),
+        })?;
+        *self.slot = Some(Shape::Dict(Box::new(entry.unwrap_or(Shape::String))));
+        Ok(value)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, TraceError> {
+        let omitted = self
+            .trace
+            .omit
+            .as_ref()
+            .filter(|(path, _)| *path == self.path)
+            .map(|(_, name)| *name);
+        let mut access = Fields {
+            trace: self.trace,
+            path: &self.path,
+            names: fields
+                .iter()
+                .copied()
+                .filter(|name| Some(*name) != omitted)
+                .collect(),
+            shapes: Vec::new(),
+        };
+        let value = visitor.visit_map(&mut access)?;
+
+        let shapes = access.shapes;
+        *self.slot = Some(Shape::Record(
+            access
+                .names
+                .iter()
+                .zip(shapes)
+                .map(|(&name, shape)| Field {
+                    name,
+                    shape: shape.unwrap_or(Shape::String),
+                    required: self.trace.required.contains(&join(&self.path, name)),
+                })
+                .collect(),
+        ));
+        Ok(value)
+    }
+
+    forward_to_deserialize_any! {
+        i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char bytes byte_buf
+        unit unit_struct newtype_struct tuple tuple_struct enum identifier ignored_any
+    }
+}
+
+struct OneItem<'t, 's> {
+    item: Option<Tracer<'t, 's>>,
+}
+
+impl<'de> SeqAccess<'de> for OneItem<'_, '_> {
+    type Error = TraceError;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, TraceError> {
+        match self.item.take() {
+            Some(tracer) => seed.deserialize(tracer).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+struct OneEntry<'t, 's> {
+    key_taken: bool,
+    value: Option<Tracer<'t, 's>>,
+}
+
+impl<'de> MapAccess<'de> for OneEntry<'_, '_> {
+    type Error = TraceError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, TraceError> {
+        if self.key_taken {
+            return Ok(None);
+        }
+        self.key_taken = true;
+        seed.deserialize("*".into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, TraceError> {
+        let tracer = self
+            .value
+            .take()
+            .ok_or_else(|| TraceError::Other("value requested twice".into()))?;
+        seed.deserialize(tracer)
+    }
+}
+
+/// Hands a struct each of its fields once, tracing the values.
+struct Fields<'t, 'p> {
+    trace: &'t Trace,
+    path: &'p str,
+    names: Vec<&'static str>,
+    shapes: Vec<Option<Shape>>,
+}
+
+impl<'de> MapAccess<'de> for Fields<'_, '_> {
+    type Error = TraceError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, TraceError> {
+        match self.names.get(self.shapes.len()) {
+            Some(&name) => seed.deserialize(name.into_deserializer()).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, TraceError> {
+        let name = self.names[self.shapes.len()];
+        self.shapes.push(None);
+        let slot = self.shapes.last_mut().expect("just pushed");
+        seed.deserialize(Tracer {
+            trace: self.trace,
+            path: join(self.path, name),
+            slot,
+        })
+    }
+}
@@ -439,3 +439,320 @@ fn test_environment_spec_defaults() {
     assert!(node.components.is_empty());
     assert!(node.targets.is_empty());
 }
+
+fn provider_context(known: &[&str], enabled: &[&str]) -> DiagnosticContext {
+    DiagnosticContext {
+        known_providers: known.iter().map(|id| id.to_string()).collect(),
+        enabled_providers: enabled.iter().map(|id| id.to_string()).collect(),
+    }
+}
+
+fn codes(diagnostics: &[ManifestDiagnostic]) -> Vec<&str> {
+    diagnostics.iter().map(|d| d.code.as_str()).collect()
+}
+
+#[test]
+fn test_diagnostics_report_yaml_problems_with_spans() {
+    let yaml = r#"project:
+  name: demo
+  verison: 1.0.0
+environments:
+  node:
+    version: ">=20 <22"
+    provider: fnm
+  python:
+    version: "^abc"
+  cobol:
+    version: "1"
+"#;
+    let context = provider_context(&["fnm", "pyenv"], &["pyenv"]);
+    let diagnostics = diagnose_manifest(yaml, ManifestFormat::Yaml, &context, None);
+    assert_eq!(
+        codes(&diagnostics),
+        vec![
+            "unknown_key",
+            "provider_not_enabled",
+            "invalid_version_constraint",
+            "unknown_env_type"
+        ]
+    );
+
+    let unknown = &diagnostics[0];
+    assert_eq!(unknown.severity, DiagnosticSeverity::Warning);
+    assert_eq!(unknown.path.as_deref(), Some("project.verison"));
+    assert!(unknown.message.contains("did you mean `version`"));
+    assert_eq!(
+        unknown.range,
+        TextRange {
+            start_line: 3,
+            start_column: 3,
+            end_line: 3,
+            end_column: 10,
+        }
+    );
+
+    let provider = &diagnostics[1];
+    assert_eq!(provider.range.start_line, 7);
+    assert_eq!(provider.range.start_column, 15);
+
+    let constraint = &diagnostics[2];
+    assert_eq!(constraint.severity, DiagnosticSeverity::Error);
+    assert_eq!(
+        constraint.path.as_deref(),
+        Some("environments.python.version")
+    );
+    assert_eq!(constraint.range.start_line, 9);
+    assert_eq!(constraint.range.start_column, 14);
+
+    assert_eq!(diagnostics[3].range.start_line, 10);
+}
+
+#[test]
+fn test_diagnostics_report_types_and_missing_fields() {
+    let yaml = r#"packages:
+  - name: git
+  - version: "1"
+  - 42
+"#;
+    let diagnostics = diagnose_manifest(
+        yaml,
+        ManifestFormat::Yaml,
+        &DiagnosticContext::default(),
+        None,
+    );
+    assert_eq!(codes(&diagnostics), vec!["missing_field", "invalid_type"]);
+    assert_eq!(diagnostics[0].path.as_deref(), Some("packages[1]"));
+    assert_eq!(diagnostics[0].range.start_line, 3);
+    assert_eq!(diagnostics[1].range.start_line, 4);
+    assert_eq!(diagnostics[1].range.start_column, 5);
+}
+
+#[test]
+fn test_schema_is_traced_from_manifest_types() {
+    use super::schema::{root_shape, Shape};
+
+    fn required(shape: &Shape) -> Vec<&'static str> {
+        match shape {
+            Shape::Record(fields) | Shape::StringOr(fields) => fields
+                .iter()
+                .filter(|f| f.required)
+                .map(|f| f.name)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    let yaml = root_shape(ManifestFormat::Yaml);
+    assert!(required(yaml).is_empty());
+    let env = yaml.field("environments").and_then(|s| s.field("node"));
+    assert_eq!(required(env.unwrap()), vec!["version"]);
+    let custom = yaml.field("custom_packages").and_then(Shape::item).unwrap();
+    assert_eq!(required(custom), vec!["name", "source"]);
+    let package = yaml.field("packages").and_then(Shape::item).unwrap();
+    assert!(matches!(package, Shape::StringOr(_)));
+    assert_eq!(required(package), vec!["name"]);
+    assert!(matches!(package.field("optional"), Some(Shape::Bool)));
+    let hook = yaml
+        .field("hooks")
+        .and_then(|s| s.field("post_install"))
+        .and_then(Shape::item)
+        .unwrap();
+    assert_eq!(required(hook), vec!["run"]);
+
+    let toml = root_shape(ManifestFormat::Toml);
+    assert!(matches!(
+        toml.field("environments").and_then(|s| s.field("node")),
+        Some(Shape::String)
+    ));
+    assert!(toml
+        .field("project")
+        .unwrap()
+        .field("description")
+        .is_none());
+}
+
+#[test]
+fn test_diagnostics_for_toml_manifest() {
+    let toml = r#"[project]
+name = "demo"
+
+[environments]
+node = "20"
+rust = "stable"
+bogus = 3
+
+[tools]
+"#;
+    let diagnostics = diagnose_manifest(
+        toml,
+        ManifestFormat::Toml,
+        &DiagnosticContext::default(),
+        None,
+    );
+    assert_eq!(
+        codes(&diagnostics),
+        vec!["unknown_env_type", "invalid_type", "unknown_key"]
+    );
+    assert_eq!(diagnostics[1].path.as_deref(), Some("environments.bogus"));
+    assert_eq!(diagnostics[1].range.start_line, 7);
+    assert_eq!(diagnostics[1].range.start_column, 9);
+    assert_eq!(diagnostics[2].range.start_line, 9);
+}
+
+#[test]
+fn test_diagnostics_report_parse_errors() {
+    let diagnostics = diagnose_manifest(
+        "project:\n  name: [demo\n",
+        ManifestFormat::Yaml,
+        &DiagnosticContext::default(),
+        None,
+    );
+    assert_eq!(codes(&diagnostics), vec!["parse_error"]);
+    assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Error);
+}
+
+#[test]
+fn test_diagnostics_detect_lockfile_drift() {
+    use crate::config::lockfile::Lockfile;
+
+    let yaml = "environments:\n  node:\n    version: \"20\"\n  python:\n    version: \"3.12\"\n";
+    let mut lockfile = Lockfile::new();
+    lockfile.lock_environment("node", "18.19.0", "fnm");
+    lockfile.lock_environment("go", "1.22.0", "goenv");
+    lockfile.record_manifest("environments: {}\n");
+
+    let diagnostics = diagnose_manifest(
+        yaml,
+        ManifestFormat::Yaml,
+        &DiagnosticContext::default(),
+        Some(&lockfile),
+    );
+    let mut found = codes(&diagnostics);
+    found.sort();
+    assert_eq!(
+        found,
+        vec![
+            "lockfile_drift",
+            "lockfile_env_mismatch",
+            "lockfile_env_missing",
+            "lockfile_env_stale"
+        ]
+    );
+
+    lockfile.lock_environment("node", "20.11.1", "fnm");
+    lockfile.lock_environment("python", "3.12.2", "pyenv");
+    lockfile.environments.remove("go");
+    lockfile.record_manifest(yaml);
+    let diagnostics = diagnose_manifest(
+        yaml,
+        ManifestFormat::Yaml,
+        &DiagnosticContext::default(),
+        Some(&lockfile),
+    );
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+}
+
+#[tokio::test]
+async fn test_diagnose_toml_manifest_against_saved_lockfile() {
+    use crate::config::lockfile::{Lockfile, LOCKFILE_FILENAME};
+
+    let dir = tempfile::tempdir().unwrap();
+    let manifest = dir.path().join(TOML_MANIFEST_FILENAME);
+    std::fs::write(&manifest, "[environments]\nnode = \"20\"\n").unwrap();
+    let mut lockfile = Lockfile::new();
+    lockfile.lock_environment("node", "20.11.1", "fnm");
+    lockfile
+        .save(&dir.path().join(LOCKFILE_FILENAME))
+        .await
+        .unwrap();
+
+    let context = DiagnosticContext::default();
+    let report = diagnose_manifest_file(&manifest, &context).await.unwrap();
+    assert!(report.lockfile_path.is_some());
+    assert!(report.diagnostics.is_empty(), "{:?}", report.diagnostics);
+
+    std::fs::write(&manifest, "[environments]\nnode = \"22\"\n").unwrap();
+    let report = diagnose_manifest_file(&manifest, &context).await.unwrap();
+    let mut found = codes(&report.diagnostics);
+    found.sort();
+    assert_eq!(found, vec!["lockfile_drift", "lockfile_env_mismatch"]);
+}
+
+#[test]
+fn test_format_yaml_orders_keys_and_keeps_comments() {
+    let yaml = r#"# Project manifest
+
+hooks:
+  post_install:
+    - run: echo done
+
+environments:
+  # pinned for CI
+  python:
+    provider: pyenv
+    version: "3.12"
+  node:
+    version: "20" # LTS
+
+project:
+  name: demo
+"#;
+    let expected = r#"# Project manifest
+
+project:
+  name: demo
+
+environments:
+  node:
+    version: "20" # LTS
+  # pinned for CI
+  python:
+    version: "3.12"
+    provider: pyenv
+
+hooks:
+  post_install:
+    - run: echo done
+"#;
+    let formatted = format_manifest(yaml, ManifestFormat::Yaml).unwrap();
+    assert_eq!(formatted, expected);
+    assert_eq!(
+        format_manifest(&formatted, ManifestFormat::Yaml).unwrap(),
+        formatted
+    );
+}
+
+#[test]
+fn test_format_toml_orders_tables_and_keys() {
+    let toml = r#"# Cognia manifest
+
+[environments]
+rust = "stable"
+# LTS line
+node = "20"
+
+[project]
+version = "0.1.0"
+name = "demo"
+"#;
+    let expected = r#"# Cognia manifest
+
+[project]
+name = "demo"
+version = "0.1.0"
+
+[environments]
+# LTS line
+node = "20"
+rust = "stable"
+"#;
+    assert_eq!(
+        format_manifest(toml, ManifestFormat::Toml).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn test_format_refuses_invalid_manifest() {
+    assert!(format_manifest("project: [demo\n", ManifestFormat::Yaml).is_err());
+}
@@ -2,6 +2,7 @@
 use std::collections::HashMap;
 
 pub const MANIFEST_FILENAME: &str = "CogniaLauncher.yaml";
+pub const TOML_MANIFEST_FILENAME: &str = "cognia.toml";
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Manifest {
@@ -111,3 +112,20 @@ pub struct HookCommand {
     #[serde(default)]
     pub platforms: Vec<String>,
 }
+
+/// The lighter `cognia.toml` manifest: bare version strings and package names
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TomlManifest {
+    #[serde(default)]
+    pub project: TomlProjectInfo,
+    #[serde(default)]
+    pub environments: HashMap<String, String>,
+    #[serde(default)]
+    pub packages: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TomlProjectInfo {
+    pub name: Option<String>,
+    pub version: Option<String>,
+}
@@ -1,6 +1,10 @@
 mod behavior;
+mod diff;
+mod migration;
 mod types;
 
+pub use diff::*;
+pub use migration::*;
 pub use types::*;
 
 #[cfg(test)]
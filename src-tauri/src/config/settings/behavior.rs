@@ -1,4 +1,6 @@
 use super::types::*;
+use crate::core::notifications::{NotificationCategory, NotificationDelivery};
+use crate::core::pin_file::PinFileFormat;
 use crate::error::{CogniaError, CogniaResult};
 use crate::platform::fs;
 use crate::tray::{
@@ -6,7 +8,8 @@
     TrayQuickAction,
 };
 use reqwest::Url;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 impl Settings {
     pub fn config_path() -> Option<PathBuf> {
@@ -31,10 +34,15 @@ pub async fn save(&self) -> CogniaResult<()> {
         let path = Self::config_path()
             .ok_or_else(|| CogniaError::Config("Could not determine config path".into()))?;
 
-        let content = toml::to_string_pretty(self)
+        self.save_to(&path).await
+    }
+
+    pub(super) async fn save_to(&self, path: &Path) -> CogniaResult<()> {
+        let content = self
+            .to_toml_string()
             .map_err(|e| CogniaError::Config(format!("Failed to serialize config: {}", e)))?;
 
-        fs::write_file_atomic(&path, content.as_bytes()).await?;
+        fs::write_file_atomic(path, content.as_bytes()).await?;
 
         Ok(())
     }
@@ -113,6 +121,78 @@ pub fn set_provider_secret_saved(&mut self, provider: &str, saved: bool) {
         }
     }
 
+    /// A copy for exports, with plaintext provider tokens replaced by the
+    /// `secret_saved` reference so the token itself never leaves the machine
+    pub fn without_secrets(&self) -> Self {
+        let mut settings = self.clone();
+        let providers: Vec<String> = settings
+            .providers
+            .iter()
+            .filter(|(_, provider)| provider.extra.contains_key("token"))
+            .map(|(name, _)| name.clone())
+            .collect();
+        for provider in providers {
+            settings.clear_provider_legacy_token(&provider);
+            settings.set_provider_secret_saved(&provider, true);
+        }
+        settings
+    }
+
+    /// User-defined env type → winget package ID mappings
+    pub fn get_winget_env_packages(&self) -> HashMap<String, String> {
+        self.providers
+            .get("winget")
+            .and_then(|settings| settings.extra.get("env_packages"))
+            .and_then(|value| value.as_table())
+            .map(|table| {
+                table
+                    .iter()
+                    .filter_map(|(env_type, id)| {
+                        id.as_str().map(|id| (env_type.clone(), id.to_string()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn set_winget_env_packages(&mut self, value: &str) -> CogniaResult<()> {
+        let packages: HashMap<String, String> = if value.trim().is_empty() {
+            HashMap::new()
+        } else {
+            serde_json::from_str(value).map_err(|_| {
+                CogniaError::Config(
+                    "winget env packages must be a JSON object of env type to package ID".into(),
+                )
+            })?
+        };
+        if let Some((env_type, _)) = packages.iter().find(|(env_type, id)| {
+            env_type.trim().is_empty() || id.trim().is_empty() || id.contains(char::is_whitespace)
+        }) {
+            return Err(CogniaError::Config(format!(
+                "Invalid winget package mapping for '{}'",
+                env_type
+            )));
+        }
+
+        if packages.is_empty() {
+            if let Some(settings) = self.providers.get_mut("winget") {
+                settings.extra.remove("env_packages");
+            }
+            self.prune_provider_entry_if_empty("winget");
+        } else {
+            let table = packages
+                .into_iter()
+                .map(|(env_type, id)| (env_type, toml::Value::String(id)))
+                .collect();
+            self.providers
+                .entry("winget".to_string())
+                .or_default()
+                .extra
+                .insert("env_packages".to_string(), toml::Value::Table(table));
+        }
+        Ok(())
+    }
+
     pub fn get_provider_enabled_effective(&self, provider: &str) -> Option<bool> {
         self.get_provider_enabled_override(provider).or_else(|| {
             if self
@@ -173,6 +253,40 @@ pub fn set_provider_priority_override(&mut self, provider: &str, priority: Optio
         }
     }
 
+    /// Pin file format `env_use_local` uses in `project_dir`: the one last
+    /// chosen for that project, else `general.pin_file_format`.
+    pub fn get_pin_file_format(&self, project_dir: &Path) -> PinFileFormat {
+        self.general
+            .project_pin_file_formats
+            .get(&project_key(project_dir))
+            .copied()
+            .unwrap_or(self.general.pin_file_format)
+    }
+
+    /// Remembers `format` for `project_dir`. Returns whether it changed.
+    pub fn set_project_pin_file_format(
+        &mut self,
+        project_dir: &Path,
+        format: PinFileFormat,
+    ) -> bool {
+        self.general
+            .project_pin_file_formats
+            .insert(project_key(project_dir), format)
+            != Some(format)
+    }
+
+    /// Record user approval for the current command line of the smoke test
+    /// at `index` in `general.smoke_tests`
+    pub fn approve_smoke_test(&mut self, index: usize) -> CogniaResult<()> {
+        let test = self
+            .general
+            .smoke_tests
+            .get_mut(index)
+            .ok_or_else(|| CogniaError::Config(format!("Smoke test {} not found", index)))?;
+        test.approved_command = Some(test.command_signature());
+        Ok(())
+    }
+
     pub fn get_root_dir(&self) -> PathBuf {
         self.paths
             .root
@@ -204,31 +318,10 @@ pub fn get_state_dir(&self) -> PathBuf {
     }
 
     pub(super) fn load_from_toml_with_tray_fallback(content: &str) -> CogniaResult<Self> {
-        match toml::from_str(content) {
-            Ok(settings) => Ok(settings),
-            Err(parse_error) => {
-                let mut raw: toml::Value = toml::from_str(content).map_err(|_| {
-                    CogniaError::Parse(format!("Failed to parse config: {}", parse_error))
-                })?;
-
-                if !Self::sanitize_tray_fields(&mut raw) {
-                    return Err(CogniaError::Parse(format!(
-                        "Failed to parse config: {}",
-                        parse_error
-                    )));
-                }
-
-                raw.try_into().map_err(|retry_error| {
-                    CogniaError::Parse(format!(
-                        "Failed to parse config after tray fallback: {}",
-                        retry_error
-                    ))
-                })
-            }
-        }
+        Self::parse_versioned(content).map(|(settings, _)| settings)
     }
 
-    fn sanitize_tray_fields(raw: &mut toml::Value) -> bool {
+    pub(super) fn sanitize_tray_fields(raw: &mut toml::Table) -> bool {
         let Some(tray) = raw.get_mut("tray").and_then(|value| value.as_table_mut()) else {
             return false;
         };
@@ -569,6 +662,14 @@ fn normalize_optional_proxy_url(value: &str) -> CogniaResult<Option<String>> {
         Ok(Some(trimmed.to_string()))
     }
 
+    fn normalize_provider_proxy(value: &str) -> CogniaResult<Option<String>> {
+        let trimmed = value.trim();
+        if trimmed.eq_ignore_ascii_case(crate::platform::proxy::DIRECT_PROXY) {
+            return Ok(Some(crate::platform::proxy::DIRECT_PROXY.to_string()));
+        }
+        Self::normalize_optional_proxy_url(trimmed)
+    }
+
     fn normalize_optional_no_proxy(value: &str) -> Option<String> {
         let normalized = value.replace(';', ",");
         let entries: Vec<String> = normalized
@@ -596,6 +697,17 @@ fn parse_update_source_mode(value: &str) -> CogniaResult<UpdateSourceMode> {
         }
     }
 
+    fn parse_update_channel(value: &str) -> CogniaResult<UpdateChannel> {
+        match value.trim().to_lowercase().as_str() {
+            "stable" => Ok(UpdateChannel::Stable),
+            "beta" => Ok(UpdateChannel::Beta),
+            "nightly" => Ok(UpdateChannel::Nightly),
+            _ => Err(CogniaError::Config(
+                "Invalid updates update_channel value. Valid: stable, beta, nightly".into(),
+            )),
+        }
+    }
+
     fn validate_update_endpoint_template(endpoint: &str) -> CogniaResult<String> {
         let trimmed = endpoint.trim();
         if trimmed.is_empty() {
@@ -607,6 +719,7 @@ fn validate_update_endpoint_template(endpoint: &str) -> CogniaResult<String> {
         // Allow updater templates while still validating URL structure.
         let normalized = trimmed
             .replace("{{current_version}}", "0.0.0")
+            .replace("{{channel}}", "stable")
             .replace("{{target}}", "windows-x86_64")
             .replace("{{arch}}", "x86_64")
             .replace("{{bundle_type}}", "nsis");
@@ -623,6 +736,96 @@ fn validate_update_endpoint_template(endpoint: &str) -> CogniaResult<String> {
         Ok(trimmed.to_string())
     }
 
+    fn validate_asset_pattern(pattern: &str) -> CogniaResult<()> {
+        regex::RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .map(|_| ())
+            .map_err(|e| CogniaError::Config(format!("Invalid asset pattern '{}': {}", pattern, e)))
+    }
+
+    fn parse_asset_match_rules(value: &str) -> CogniaResult<Vec<AssetMatchRule>> {
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rules: Vec<AssetMatchRule> = serde_json::from_str(trimmed).map_err(|_| {
+            CogniaError::Config("Invalid JSON array for general.asset_match_rules".into())
+        })?;
+        for rule in &rules {
+            Self::validate_asset_pattern(&rule.pattern)?;
+        }
+        Ok(rules)
+    }
+
+    fn parse_smoke_tests(value: &str) -> CogniaResult<Vec<SmokeTestDefinition>> {
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tests: Vec<SmokeTestDefinition> = serde_json::from_str(trimmed).map_err(|_| {
+            CogniaError::Config("Invalid JSON array for general.smoke_tests".into())
+        })?;
+        for test in &tests {
+            if test.env_type.trim().is_empty() || test.command.trim().is_empty() {
+                return Err(CogniaError::Config(
+                    "Smoke tests need an env type and a command".into(),
+                ));
+            }
+        }
+        Ok(tests)
+    }
+
+    /// JSON array or one pattern per line (patterns may contain commas)
+    fn parse_asset_exclude_patterns(value: &str) -> CogniaResult<Vec<String>> {
+        let trimmed = value.trim();
+        let patterns: Vec<String> = if trimmed.is_empty() {
+            Vec::new()
+        } else if trimmed.starts_with('[') {
+            serde_json::from_str(trimmed).map_err(|_| {
+                CogniaError::Config("Invalid JSON array for general.asset_exclude_patterns".into())
+            })?
+        } else {
+            trimmed
+                .lines()
+                .map(|line| line.trim().to_string())
+                .filter(|line| !line.is_empty())
+                .collect()
+        };
+        for pattern in &patterns {
+            Self::validate_asset_pattern(pattern)?;
+        }
+        Ok(patterns)
+    }
+
+    /// JSON array or comma/line separated list, trimmed and deduplicated
+    /// case-insensitively
+    fn parse_secret_key_patterns(value: &str) -> CogniaResult<Vec<String>> {
+        let trimmed = value.trim();
+        let raw: Vec<String> = if trimmed.starts_with('[') {
+            serde_json::from_str(trimmed).map_err(|_| {
+                CogniaError::Config("Invalid JSON array for envvar.secret_key_patterns".into())
+            })?
+        } else {
+            trimmed.split([',', '\n']).map(str::to_string).collect()
+        };
+
+        let mut patterns: Vec<String> = Vec::with_capacity(raw.len());
+        for pattern in raw {
+            let pattern = pattern.trim();
+            if !pattern.is_empty()
+                && !patterns
+                    .iter()
+                    .any(|existing| existing.eq_ignore_ascii_case(pattern))
+            {
+                patterns.push(pattern.to_string());
+            }
+        }
+        Ok(patterns)
+    }
+
     fn parse_update_custom_endpoints(value: &str) -> CogniaResult<Vec<String>> {
         let trimmed = value.trim();
         if trimmed.is_empty() {
@@ -654,11 +857,40 @@ fn parse_update_custom_endpoints(value: &str) -> CogniaResult<Vec<String>> {
         Ok(validated)
     }
 
+    fn parse_marketplace_trusted_keys(value: &str) -> CogniaResult<Vec<String>> {
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let raw_keys: Vec<String> = serde_json::from_str(trimmed).map_err(|_| {
+            CogniaError::Config("Invalid JSON array for plugin.marketplace_trusted_keys".into())
+        })?;
+
+        let mut keys = Vec::with_capacity(raw_keys.len());
+        for key in raw_keys {
+            let normalized = key.trim().to_ascii_lowercase();
+            if normalized.len() != 64 || !normalized.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(CogniaError::Config(format!(
+                    "Invalid marketplace trusted key '{}' (expected 64 hex characters)",
+                    key
+                )));
+            }
+            if !keys.contains(&normalized) {
+                keys.push(normalized);
+            }
+        }
+        Ok(keys)
+    }
+
     pub fn get_value(&self, key: &str) -> Option<String> {
         let parts: Vec<&str> = key.split('.').collect();
 
         match parts.as_slice() {
             ["general", "parallel_downloads"] => Some(self.general.parallel_downloads.to_string()),
+            ["general", "parallel_env_installs"] => {
+                Some(self.general.parallel_env_installs.to_string())
+            }
             ["general", "resolve_strategy"] => Some(
                 match self.general.resolve_strategy {
                     ResolveStrategy::Latest => "latest",
@@ -690,12 +922,61 @@ pub fn get_value(&self, key: &str) -> Option<String> {
             ["general", "cache_monitor_external"] => {
                 Some(self.general.cache_monitor_external.to_string())
             }
+            ["general", "cache_monthly_summary"] => {
+                Some(self.general.cache_monthly_summary.to_string())
+            }
             ["general", "download_speed_limit"] => {
                 Some(self.general.download_speed_limit.to_string())
             }
+            ["general", "download_monthly_cap_mb"] => {
+                Some(self.general.download_monthly_cap_mb.to_string())
+            }
+            ["general", "download_history_max_age_days"] => {
+                Some(self.general.download_history_max_age_days.to_string())
+            }
+            ["general", "download_history_max_records"] => {
+                Some(self.general.download_history_max_records.to_string())
+            }
+            ["general", "trash_retention_days"] => {
+                Some(self.general.trash_retention_days.to_string())
+            }
+            ["general", "download_auto_resume"] => {
+                Some(self.general.download_auto_resume.to_string())
+            }
+            ["general", "progress_event_interval_ms"] => {
+                Some(self.general.progress_event_interval_ms.to_string())
+            }
+            ["general", "extract_symlink_mode"] => {
+                Some(self.general.extract_symlink_mode.to_string())
+            }
+            ["general", "pin_file_format"] => Some(self.general.pin_file_format.to_string()),
+            ["general", "python_tool_backend"] => {
+                Some(self.general.python_tool_backend.to_string())
+            }
             ["general", "update_check_concurrency"] => {
                 Some(self.general.update_check_concurrency.to_string())
             }
+            ["general", "metrics_listener_enabled"] => {
+                Some(self.general.metrics_listener_enabled.to_string())
+            }
+            ["general", "metrics_listener_port"] => {
+                Some(self.general.metrics_listener_port.to_string())
+            }
+            ["general", "local_api_enabled"] => Some(self.general.local_api_enabled.to_string()),
+            ["general", "local_api_rate_limit_per_minute"] => {
+                Some(self.general.local_api_rate_limit_per_minute.to_string())
+            }
+            ["general", "asset_match_rules"] => Some(
+                serde_json::to_string(&self.general.asset_match_rules)
+                    .unwrap_or_else(|_| "[]".into()),
+            ),
+            ["general", "asset_exclude_patterns"] => Some(
+                serde_json::to_string(&self.general.asset_exclude_patterns)
+                    .unwrap_or_else(|_| "[]".into()),
+            ),
+            ["general", "smoke_tests"] => Some(
+                serde_json::to_string(&self.general.smoke_tests).unwrap_or_else(|_| "[]".into()),
+            ),
             ["network", "timeout"] => Some(self.network.timeout.to_string()),
             ["network", "retries"] => Some(self.network.retries.to_string()),
             ["network", "proxy"] => self.network.proxy.clone(),
@@ -704,6 +985,12 @@ pub fn get_value(&self, key: &str) -> Option<String> {
                 .no_proxy
                 .clone()
                 .or_else(|| Some(String::new())),
+            ["network", "provider_proxies", provider] => self
+                .network
+                .provider_proxies
+                .get(&provider.to_lowercase())
+                .cloned(),
+            ["network", "offline_mode"] => Some(self.network.offline_mode.to_string()),
             ["security", "allow_http"] => Some(self.security.allow_http.to_string()),
             ["security", "verify_certificates"] => {
                 Some(self.security.verify_certificates.to_string())
@@ -762,6 +1049,7 @@ pub fn get_value(&self, key: &str) -> Option<String> {
             ["updates", "fallback_to_official"] => {
                 Some(self.updates.fallback_to_official.to_string())
             }
+            ["updates", "update_channel"] => Some(self.updates.update_channel.as_str().to_string()),
             ["tray", "minimize_to_tray"] => Some(self.tray.minimize_to_tray.to_string()),
             ["tray", "start_minimized"] => Some(self.tray.start_minimized.to_string()),
             ["tray", "show_notifications"] => Some(self.tray.show_notifications.to_string()),
@@ -811,6 +1099,10 @@ pub fn get_value(&self, key: &str) -> Option<String> {
             ["envvar", "default_scope"] => Some(self.envvar.default_scope.clone()),
             ["envvar", "auto_snapshot"] => Some(self.envvar.auto_snapshot.to_string()),
             ["envvar", "mask_sensitive"] => Some(self.envvar.mask_sensitive.to_string()),
+            ["envvar", "secret_key_patterns"] => Some(
+                serde_json::to_string(&self.envvar.secret_key_patterns)
+                    .unwrap_or_else(|_| "[]".into()),
+            ),
             ["tray", "menu_priority_items"] => Some(
                 serde_json::to_string(&self.tray.menu_priority_items)
                     .unwrap_or_else(|_| "[]".into()),
@@ -833,6 +1125,19 @@ pub fn get_value(&self, key: &str) -> Option<String> {
                 .no_proxy
                 .clone()
                 .or_else(|| Some(String::new())),
+            ["terminal", "max_sessions"] => Some(self.terminal.max_sessions.to_string()),
+            ["terminal", "launch_history_max"] => {
+                Some(self.terminal.launch_history_max.to_string())
+            }
+            ["terminal", "launch_history_record_env_values"] => {
+                Some(self.terminal.launch_history_record_env_values.to_string())
+            }
+            ["terminal", "launch_sample_interval_secs"] => {
+                Some(self.terminal.launch_sample_interval_secs.to_string())
+            }
+            ["terminal", "terminate_launches_on_exit"] => {
+                Some(self.terminal.terminate_launches_on_exit.to_string())
+            }
             ["log", "max_retention_days"] => Some(self.log.max_retention_days.to_string()),
             ["log", "max_total_size_mb"] => Some(self.log.max_total_size_mb.to_string()),
             ["log", "auto_cleanup"] => Some(self.log.auto_cleanup.to_string()),
@@ -853,6 +1158,13 @@ pub fn get_value(&self, key: &str) -> Option<String> {
             ["plugin", "permission_enforcement_mode"] => {
                 Some(self.plugin.permission_enforcement_mode.clone())
             }
+            ["plugin", "marketplace_index_url"] => Some(self.plugin.marketplace_index_url.clone()),
+            ["plugin", "marketplace_trusted_keys"] => Some(
+                serde_json::to_string(&self.plugin.marketplace_trusted_keys)
+                    .unwrap_or_else(|_| "[]".into()),
+            ),
+            ["plugin", "developer_mode"] => Some(self.plugin.developer_mode.to_string()),
+            ["plugin", "storage_quota_mb"] => Some(self.plugin.storage_quota_mb.to_string()),
             ["startup", "scan_environments"] => Some(self.startup.scan_environments.to_string()),
             ["startup", "scan_packages"] => Some(self.startup.scan_packages.to_string()),
             ["startup", "max_concurrent_scans"] => {
@@ -866,7 +1178,21 @@ pub fn get_value(&self, key: &str) -> Option<String> {
             ["shortcuts", "toggle_window"] => Some(self.shortcuts.toggle_window.clone()),
             ["shortcuts", "command_palette"] => Some(self.shortcuts.command_palette.clone()),
             ["shortcuts", "quick_search"] => Some(self.shortcuts.quick_search.clone()),
+            ["notifications", category] => NotificationCategory::parse(category).map(|category| {
+                self.notifications
+                    .delivery_for(category)
+                    .as_str()
+                    .to_string()
+            }),
             ["providers", _provider, "token"] => None,
+            ["providers", "winget", "env_packages"] => {
+                let packages = self.get_winget_env_packages();
+                if packages.is_empty() {
+                    None
+                } else {
+                    serde_json::to_string(&packages).ok()
+                }
+            }
             ["providers", provider, "url"] => self
                 .providers
                 .get(*provider)
@@ -903,6 +1229,17 @@ pub fn set_value(&mut self, key: &str, value: &str) -> CogniaResult<()> {
                     CogniaError::Config("Invalid value for parallel_downloads".into())
                 })?;
             }
+            ["general", "parallel_env_installs"] => {
+                let v: u32 = value.parse().map_err(|_| {
+                    CogniaError::Config("Invalid value for parallel_env_installs".into())
+                })?;
+                if !(1..=8).contains(&v) {
+                    return Err(CogniaError::Config(
+                        "parallel_env_installs must be 1-8".into(),
+                    ));
+                }
+                self.general.parallel_env_installs = v;
+            }
             ["general", "resolve_strategy"] => {
                 self.general.resolve_strategy = match value {
                     "latest" => ResolveStrategy::Latest,
@@ -966,11 +1303,79 @@ pub fn set_value(&mut self, key: &str, value: &str) -> CogniaResult<()> {
                     .parse()
                     .map_err(|_| CogniaError::Config("Invalid boolean value".into()))?;
             }
+            ["general", "cache_monthly_summary"] => {
+                self.general.cache_monthly_summary = value
+                    .parse()
+                    .map_err(|_| CogniaError::Config("Invalid boolean value".into()))?;
+            }
             ["general", "download_speed_limit"] => {
                 self.general.download_speed_limit = value.parse().map_err(|_| {
                     CogniaError::Config("Invalid value for download_speed_limit".into())
                 })?;
             }
+            ["general", "download_auto_resume"] => {
+                self.general.download_auto_resume = value
+                    .parse()
+                    .map_err(|_| CogniaError::Config("Invalid boolean value".into()))?;
+            }
+            ["general", "progress_event_interval_ms"] => {
+                let v: u64 = value.parse().map_err(|_| {
+                    CogniaError::Config("Invalid value for progress_event_interval_ms".into())
+                })?;
+                if !(16..=5000).contains(&v) {
+                    return Err(CogniaError::Config(
+                        "progress_event_interval_ms must be 16-5000".into(),
+                    ));
+                }
+                self.general.progress_event_interval_ms = v;
+            }
+            ["general", "extract_symlink_mode"] => {
+                self.general.extract_symlink_mode = value.parse()?;
+            }
+            ["general", "pin_file_format"] => {
+                self.general.pin_file_format = value.parse()?;
+            }
+            ["general", "python_tool_backend"] => {
+                self.general.python_tool_backend = value.parse()?;
+            }
+            ["general", "download_monthly_cap_mb"] => {
+                self.general.download_monthly_cap_mb = value.parse().map_err(|_| {
+                    CogniaError::Config("Invalid value for download_monthly_cap_mb".into())
+                })?;
+            }
+            ["general", "download_history_max_age_days"] => {
+                let v: u32 = value.parse().map_err(|_| {
+                    CogniaError::Config("Invalid value for download_history_max_age_days".into())
+                })?;
+                if v > 3650 {
+                    return Err(CogniaError::Config(
+                        "download_history_max_age_days must be between 0 and 3650".into(),
+                    ));
+                }
+                self.general.download_history_max_age_days = v;
+            }
+            ["general", "download_history_max_records"] => {
+                let v: usize = value.parse().map_err(|_| {
+                    CogniaError::Config("Invalid value for download_history_max_records".into())
+                })?;
+                if !(10..=1000).contains(&v) {
+                    return Err(CogniaError::Config(
+                        "download_history_max_records must be between 10 and 1000".into(),
+                    ));
+                }
+                self.general.download_history_max_records = v;
+            }
+            ["general", "trash_retention_days"] => {
+                let v: u32 = value.parse().map_err(|_| {
+                    CogniaError::Config("Invalid value for trash_retention_days".into())
+                })?;
+                if v > 3650 {
+                    return Err(CogniaError::Config(
+                        "trash_retention_days must be between 0 and 3650".into(),
+                    ));
+                }
+                self.general.trash_retention_days = v;
+            }
             ["general", "update_check_concurrency"] => {
                 let v: u32 = value.parse().map_err(|_| {
                     CogniaError::Config("Invalid value for update_check_concurrency".into())
@@ -982,12 +1387,55 @@ pub fn set_value(&mut self, key: &str, value: &str) -> CogniaResult<()> {
                 }
                 self.general.update_check_concurrency = v;
             }
+            ["general", "metrics_listener_enabled"] => {
+                self.general.metrics_listener_enabled = value
+                    .parse()
+                    .map_err(|_| CogniaError::Config("Invalid boolean value".into()))?;
+            }
+            ["general", "metrics_listener_port"] => {
+                let v: u16 = value.parse().map_err(|_| {
+                    CogniaError::Config("Invalid value for metrics_listener_port".into())
+                })?;
+                if v < 1024 {
+                    return Err(CogniaError::Config(
+                        "metrics_listener_port must be 1024-65535".into(),
+                    ));
+                }
+                self.general.metrics_listener_port = v;
+            }
+            ["general", "local_api_enabled"] => {
+                self.general.local_api_enabled = value
+                    .parse()
+                    .map_err(|_| CogniaError::Config("Invalid boolean value".into()))?;
+            }
+            ["general", "local_api_rate_limit_per_minute"] => {
+                let v: u32 = value.parse().map_err(|_| {
+                    CogniaError::Config("Invalid value for local_api_rate_limit_per_minute".into())
+                })?;
+                if !(1..=10000).contains(&v) {
+                    return Err(CogniaError::Config(
+                        "local_api_rate_limit_per_minute must be 1-10000".into(),
+                    ));
+                }
+                self.general.local_api_rate_limit_per_minute = v;
+            }
             ["general", "custom_cache_entries"] => {
                 self.general.custom_cache_entries =
                     serde_json::from_str(value.trim()).map_err(|_| {
                         CogniaError::Config("Invalid JSON for custom_cache_entries".into())
                     })?;
             }
+            ["general", "asset_match_rules"] => {
+                self.general.asset_match_rules = Self::parse_asset_match_rules(value)?;
+            }
+            ["general", "asset_exclude_patterns"] => {
+                self.general.asset_exclude_patterns = Self::parse_asset_exclude_patterns(value)?;
+            }
+            ["general", "smoke_tests"] => {
+                let mut tests = Self::parse_smoke_tests(value)?;
+                crate::core::smoke_test::retain_approvals(&mut tests, &self.general.smoke_tests);
+                self.general.smoke_tests = tests;
+            }
             ["general", "external_cache_excluded_providers"] => {
                 let trimmed = value.trim();
                 let parsed: Vec<String> = if trimmed.is_empty() {
@@ -1031,6 +1479,25 @@ pub fn set_value(&mut self, key: &str, value: &str) -> CogniaResult<()> {
                     Some(value.to_string())
                 };
             }
+            ["network", "provider_proxies", provider] => {
+                let provider = provider.trim().to_lowercase();
+                if provider.is_empty() {
+                    return Err(CogniaError::Config("Provider id cannot be empty".into()));
+                }
+                match Self::normalize_provider_proxy(value)? {
+                    Some(proxy) => {
+                        self.network.provider_proxies.insert(provider, proxy);
+                    }
+                    None => {
+                        self.network.provider_proxies.remove(&provider);
+                    }
+                }
+            }
+            ["network", "offline_mode"] => {
+                self.network.offline_mode = value
+                    .parse()
+                    .map_err(|_| CogniaError::Config("Invalid boolean value".into()))?;
+            }
             ["security", "allow_http"] => {
                 self.security.allow_http = value
                     .parse()
@@ -1182,6 +1649,9 @@ pub fn set_value(&mut self, key: &str, value: &str) -> CogniaResult<()> {
                     .parse()
                     .map_err(|_| CogniaError::Config("Invalid boolean value".into()))?;
             }
+            ["updates", "update_channel"] => {
+                self.updates.update_channel = Self::parse_update_channel(value)?;
+            }
             ["tray", "minimize_to_tray"] => {
                 self.tray.minimize_to_tray = value
                     .parse()
@@ -1253,6 +1723,9 @@ pub fn set_value(&mut self, key: &str, value: &str) -> CogniaResult<()> {
                     .parse()
                     .map_err(|_| CogniaError::Config("Invalid boolean value".into()))?;
             }
+            ["envvar", "secret_key_patterns"] => {
+                self.envvar.secret_key_patterns = Self::parse_secret_key_patterns(value)?;
+            }
             ["terminal", "default_shell"] => {
                 self.terminal.default_shell = value.to_string();
             }
@@ -1277,6 +1750,49 @@ pub fn set_value(&mut self, key: &str, value: &str) -> CogniaResult<()> {
             ["terminal", "no_proxy"] => {
                 self.terminal.no_proxy = Self::normalize_optional_no_proxy(value);
             }
+            ["terminal", "max_sessions"] => {
+                let v: u32 = value
+                    .parse()
+                    .map_err(|_| CogniaError::Config("Invalid value for max_sessions".into()))?;
+                if !(1..=64).contains(&v) {
+                    return Err(CogniaError::Config(
+                        "max_sessions must be between 1 and 64".into(),
+                    ));
+                }
+                self.terminal.max_sessions = v;
+            }
+            ["terminal", "launch_history_max"] => {
+                let v: u32 = value.parse().map_err(|_| {
+                    CogniaError::Config("Invalid value for launch_history_max".into())
+                })?;
+                if !(1..=1000).contains(&v) {
+                    return Err(CogniaError::Config(
+                        "launch_history_max must be between 1 and 1000".into(),
+                    ));
+                }
+                self.terminal.launch_history_max = v;
+            }
+            ["terminal", "launch_history_record_env_values"] => {
+                self.terminal.launch_history_record_env_values = value
+                    .parse()
+                    .map_err(|_| CogniaError::Config("Invalid boolean value".into()))?;
+            }
+            ["terminal", "launch_sample_interval_secs"] => {
+                let v: u32 = value.parse().map_err(|_| {
+                    CogniaError::Config("Invalid value for launch_sample_interval_secs".into())
+                })?;
+                if !(1..=60).contains(&v) {
+                    return Err(CogniaError::Config(
+                        "launch_sample_interval_secs must be between 1 and 60".into(),
+                    ));
+                }
+                self.terminal.launch_sample_interval_secs = v;
+            }
+            ["terminal", "terminate_launches_on_exit"] => {
+                self.terminal.terminate_launches_on_exit = value
+                    .parse()
+                    .map_err(|_| CogniaError::Config("Invalid boolean value".into()))?;
+            }
             ["log", "max_retention_days"] => {
                 self.log.max_retention_days = value.parse().map_err(|_| {
                     CogniaError::Config("Invalid value for max_retention_days".into())
@@ -1348,6 +1864,34 @@ pub fn set_value(&mut self, key: &str, value: &str) -> CogniaResult<()> {
                 }
                 self.plugin.permission_enforcement_mode = mode;
             }
+            ["plugin", "marketplace_index_url"] => {
+                let url = value.trim();
+                if !url.is_empty() && !url.starts_with("https://") && !url.starts_with("http://") {
+                    return Err(CogniaError::Config(
+                        "marketplace_index_url must be an http(s) URL".into(),
+                    ));
+                }
+                self.plugin.marketplace_index_url = url.to_string();
+            }
+            ["plugin", "marketplace_trusted_keys"] => {
+                self.plugin.marketplace_trusted_keys = Self::parse_marketplace_trusted_keys(value)?;
+            }
+            ["plugin", "developer_mode"] => {
+                self.plugin.developer_mode = value
+                    .parse()
+                    .map_err(|_| CogniaError::Config("Invalid boolean value".into()))?;
+            }
+            ["plugin", "storage_quota_mb"] => {
+                let quota: u64 = value.parse().map_err(|_| {
+                    CogniaError::Config("Invalid value for storage_quota_mb".into())
+                })?;
+                if quota == 0 {
+                    return Err(CogniaError::Config(
+                        "storage_quota_mb must be greater than 0".into(),
+                    ));
+                }
+                self.plugin.storage_quota_mb = quota;
+            }
             ["startup", "scan_environments"] => {
                 self.startup.scan_environments = value
                     .parse()
@@ -1387,11 +1931,25 @@ pub fn set_value(&mut self, key: &str, value: &str) -> CogniaResult<()> {
             ["shortcuts", "quick_search"] => {
                 self.shortcuts.quick_search = value.to_string();
             }
+            ["notifications", category] => {
+                let category = NotificationCategory::parse(category).ok_or_else(|| {
+                    CogniaError::Config(format!("Unknown notification category: {}", category))
+                })?;
+                let delivery = NotificationDelivery::parse(value).ok_or_else(|| {
+                    CogniaError::Config(
+                        "Invalid notification delivery value (use toast, in_app or muted)".into(),
+                    )
+                })?;
+                self.notifications.set_delivery(category, delivery);
+            }
             ["providers", _provider, "token"] => {
                 return Err(CogniaError::Config(
                     "Provider tokens must be managed via secure secret storage".into(),
                 ));
             }
+            ["providers", "winget", "env_packages"] => {
+                self.set_winget_env_packages(value)?;
+            }
             ["providers", provider, "url"] => {
                 let ps = self.providers.entry(provider.to_string()).or_default();
                 if value.is_empty() {
@@ -1480,3 +2038,12 @@ pub fn should_verify_ssl(&self, provider: &str) -> bool {
             .unwrap_or(self.security.verify_certificates)
     }
 }
+
+/// Settings key for a project directory; the same project reached through a
+/// different spelling or symlink maps to the same key.
+fn project_key(project_dir: &Path) -> String {
+    std::fs::canonicalize(project_dir)
+        .unwrap_or_else(|_| project_dir.to_path_buf())
+        .display()
+        .to_string()
+}
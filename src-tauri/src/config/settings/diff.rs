@@ -0,0 +1,267 @@
+//! Per-key comparison of two [`Settings`], used to preview an import and to
+//! apply only the keys the user picked.
+
+use super::migration::SettingsLoadReport;
+use super::types::Settings;
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::env::{classify_env_var_sensitivity, mask_sensitive_value};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Prefixes whose keys only take effect after the provider registry is rebuilt
+const REGISTRY_KEY_PREFIXES: &[&str] = &["mirrors.", "providers.", "provider_settings."];
+
+/// Prefixes whose keys move data directories the download manager works in
+const DATA_DIR_KEY_PREFIXES: &[&str] = &["paths."];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigChangeKind {
+    Added,
+    Changed,
+    Removed,
+}
+
+/// One key that differs between the current and the incoming settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigChange {
+    /// Dotted config key, e.g. `general.parallel_downloads`
+    pub key: String,
+    pub kind: ConfigChangeKind,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    /// Values are masked because the key or value looks like a credential
+    pub secret: bool,
+    /// Applying the key rebuilds the provider registry or download manager
+    pub requires_reinit: bool,
+}
+
+/// What `config_import` would change, before anything is written
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigImportPreview {
+    pub changes: Vec<ConfigChange>,
+    /// Migration and validation notes for the incoming file
+    pub report: SettingsLoadReport,
+}
+
+/// Leaf values by dotted key, with the path segments needed to write them back
+type FlatSettings = BTreeMap<String, (Vec<String>, toml::Value)>;
+
+/// The table `config_export` would write for `settings`
+fn to_table(settings: &Settings) -> CogniaResult<toml::Table> {
+    let content = toml::to_string(settings)
+        .map_err(|e| CogniaError::Config(format!("Failed to serialize config: {}", e)))?;
+    toml::from_str(&content)
+        .map_err(|e| CogniaError::Config(format!("Failed to serialize config: {}", e)))
+}
+
+fn flatten(table: &toml::Table) -> FlatSettings {
+    fn walk(table: &toml::Table, path: &mut Vec<String>, out: &mut FlatSettings) {
+        for (name, value) in table {
+            path.push(name.clone());
+            match value {
+                toml::Value::Table(inner) => walk(inner, path, out),
+                leaf => {
+                    out.insert(path.join("."), (path.clone(), leaf.clone()));
+                }
+            }
+            path.pop();
+        }
+    }
+
+    let mut out = FlatSettings::new();
+    walk(table, &mut Vec::new(), &mut out);
+    // The version is rewritten on every load, never imported
+    out.remove("schema_version");
+    out
+}
+
+fn display_value(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Whether a proxy or mirror URL carries `user:password@`
+fn url_has_credentials(value: &str) -> bool {
+    value
+        .split_once("://")
+        .map(|(_, rest)| rest.split('/').next().unwrap_or(rest))
+        .and_then(|authority| authority.split_once('@'))
+        .is_some_and(|(userinfo, _)| userinfo.contains(':'))
+}
+
+fn is_secret(key: &str, value: &str) -> bool {
+    classify_env_var_sensitivity(key, value).is_some() || url_has_credentials(value)
+}
+
+/// Whether applying `key` needs the re-initialization done at startup
+pub fn config_key_requires_reinit(key: &str) -> bool {
+    config_key_rebuilds_registry(key) || config_key_moves_data_dirs(key)
+}
+
+pub fn config_key_rebuilds_registry(key: &str) -> bool {
+    REGISTRY_KEY_PREFIXES.iter().any(|p| key.starts_with(p))
+}
+
+pub fn config_key_moves_data_dirs(key: &str) -> bool {
+    DATA_DIR_KEY_PREFIXES.iter().any(|p| key.starts_with(p))
+}
+
+fn set_path(table: &mut toml::Table, path: &[String], value: toml::Value) {
+    let Some((last, parents)) = path.split_last() else {
+        return;
+    };
+    let mut current = table;
+    for name in parents {
+        let entry = current
+            .entry(name.clone())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        if !entry.is_table() {
+            *entry = toml::Value::Table(toml::Table::new());
+        }
+        let toml::Value::Table(inner) = entry else {
+            return;
+        };
+        current = inner;
+    }
+    current.insert(last.clone(), value);
+}
+
+fn remove_path(table: &mut toml::Table, path: &[String]) {
+    let Some((last, parents)) = path.split_last() else {
+        return;
+    };
+    let mut current = table;
+    for name in parents {
+        let Some(toml::Value::Table(inner)) = current.get_mut(name) else {
+            return;
+        };
+        current = inner;
+    }
+    current.remove(last);
+}
+
+/// Drop tables left empty after their leaves were removed
+fn prune_empty_tables(table: &mut toml::Table) {
+    for value in table.values_mut() {
+        if let toml::Value::Table(inner) = value {
+            prune_empty_tables(inner);
+        }
+    }
+    table.retain(|_, value| !matches!(value, toml::Value::Table(inner) if inner.is_empty()));
+}
+
+fn parse_table(table: &toml::Table) -> CogniaResult<(Settings, SettingsLoadReport)> {
+    let content = toml::to_string(table)
+        .map_err(|e| CogniaError::Config(format!("Failed to serialize config: {}", e)))?;
+    Settings::parse_versioned(&content)
+}
+
+impl Settings {
+    /// Keys that differ in `incoming`, sorted by key. Secret values are masked.
+    pub fn diff(&self, incoming: &Settings) -> CogniaResult<Vec<ConfigChange>> {
+        let current = flatten(&to_table(self)?);
+        let incoming = flatten(&to_table(incoming)?);
+
+        let mut keys: Vec<&String> = current.keys().chain(incoming.keys()).collect();
+        keys.sort();
+        keys.dedup();
+
+        Ok(keys
+            .into_iter()
+            .filter_map(|key| {
+                let old = current.get(key).map(|(_, v)| v);
+                let new = incoming.get(key).map(|(_, v)| v);
+                let kind = match (old, new) {
+                    (None, Some(_)) => ConfigChangeKind::Added,
+                    (Some(_), None) => ConfigChangeKind::Removed,
+                    (Some(a), Some(b)) if a != b => ConfigChangeKind::Changed,
+                    _ => return None,
+                };
+                let old_value = old.map(display_value);
+                let new_value = new.map(display_value);
+                let secret = [&old_value, &new_value]
+                    .into_iter()
+                    .flatten()
+                    .any(|v| is_secret(key, v));
+                let mask = |v: Option<String>| {
+                    if secret {
+                        v.map(|v| mask_sensitive_value(&v))
+                    } else {
+                        v
+                    }
+                };
+                Some(ConfigChange {
+                    key: key.clone(),
+                    kind,
+                    old_value: mask(old_value),
+                    new_value: mask(new_value),
+                    secret,
+                    requires_reinit: config_key_requires_reinit(key),
+                })
+            })
+            .collect())
+    }
+
+    /// These settings with only `keys` taken from `incoming`; a key missing
+    /// from `incoming` is removed. The result is validated like a loaded file.
+    pub fn apply_keys(
+        &self,
+        incoming: &Settings,
+        keys: &[String],
+    ) -> CogniaResult<(Settings, SettingsLoadReport)> {
+        let mut merged = to_table(self)?;
+        let current = flatten(&merged);
+        let incoming = flatten(&to_table(incoming)?);
+
+        for key in keys {
+            if let Some((path, value)) = incoming.get(key) {
+                set_path(&mut merged, path, value.clone());
+            } else if let Some((path, _)) = current.get(key) {
+                remove_path(&mut merged, path);
+            } else {
+                return Err(CogniaError::Config(format!("Unknown config key: {}", key)));
+            }
+        }
+
+        parse_table(&merged)
+    }
+
+    /// The keys accepted by `filter`, as a table shaped like the config file
+    pub fn select_keys(&self, filter: impl Fn(&str) -> bool) -> CogniaResult<toml::Table> {
+        let mut selected = toml::Table::new();
+        for (key, (path, value)) in flatten(&to_table(self)?) {
+            if filter(&key) {
+                set_path(&mut selected, &path, value);
+            }
+        }
+        Ok(selected)
+    }
+
+    /// These settings with every key accepted by `filter` taken from
+    /// `fragment`; accepted keys missing from `fragment` are removed and keys
+    /// the filter rejects are left alone. Validated like a loaded file.
+    pub fn replace_keys(
+        &self,
+        fragment: &toml::Table,
+        filter: impl Fn(&str) -> bool,
+    ) -> CogniaResult<(Settings, SettingsLoadReport)> {
+        let mut merged = to_table(self)?;
+        for (key, (path, _)) in flatten(&merged) {
+            if filter(&key) {
+                remove_path(&mut merged, &path);
+            }
+        }
+        prune_empty_tables(&mut merged);
+        for (key, (path, value)) in flatten(fragment) {
+            if filter(&key) {
+                set_path(&mut merged, &path, value);
+            }
+        }
+        parse_table(&merged)
+    }
+}
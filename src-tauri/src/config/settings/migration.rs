@@ -0,0 +1,420 @@
+//! Versioned loading of `config.toml`.
+//!
+//! A file is upgraded one schema version at a time before it is turned into
+//! [`Settings`]. Deserialization is forgiving: a value of the wrong type is
+//! reset to its default and reported, instead of the whole file being
+//! discarded.
+
+use super::types::Settings;
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::fs;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Schema version written by this release
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+/// Version assumed for files written before `schema_version` existed
+pub const UNVERSIONED_SCHEMA_VERSION: u32 = 1;
+
+/// Upgrades a raw config table from version `N` to `N + 1`
+pub type Migration = fn(&mut toml::Table);
+
+/// Migration steps, indexed by the version they upgrade from
+const MIGRATIONS: &[(u32, Migration)] = &[(1, migrate_v1_to_v2)];
+
+/// A value that could not be loaded and was reset to its default
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsValidationIssue {
+    /// Dotted config key, empty when the file as a whole was unreadable
+    pub key: String,
+    pub message: String,
+}
+
+/// What happened while loading the config file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsLoadReport {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub issues: Vec<SettingsValidationIssue>,
+    /// Copy of the original file, written before it was rewritten
+    pub backup_path: Option<String>,
+}
+
+impl SettingsLoadReport {
+    fn clean(version: u32) -> Self {
+        Self {
+            from_version: version,
+            to_version: version,
+            issues: Vec::new(),
+            backup_path: None,
+        }
+    }
+
+    pub fn migrated(&self) -> bool {
+        self.from_version < self.to_version
+    }
+
+    /// Nothing worth telling the user about
+    pub fn is_clean(&self) -> bool {
+        !self.migrated() && self.issues.is_empty()
+    }
+}
+
+/// v1 -> v2: providers disabled through the legacy
+/// `provider_settings.disabled_providers` list get an explicit
+/// `providers.<id>.enabled = false` override.
+pub fn migrate_v1_to_v2(raw: &mut toml::Table) {
+    let disabled: Vec<String> = raw
+        .get("provider_settings")
+        .and_then(|value| value.get("disabled_providers"))
+        .and_then(|value| value.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    if disabled.is_empty() {
+        return;
+    }
+
+    let Some(providers) = raw
+        .entry("providers")
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+        .as_table_mut()
+    else {
+        return;
+    };
+    for provider in disabled {
+        if let Some(entry) = providers
+            .entry(provider)
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+            .as_table_mut()
+        {
+            entry
+                .entry("enabled")
+                .or_insert(toml::Value::Boolean(false));
+        }
+    }
+}
+
+/// Read `schema_version`, falling back to [`UNVERSIONED_SCHEMA_VERSION`]
+fn schema_version(raw: &toml::Table, issues: &mut Vec<SettingsValidationIssue>) -> u32 {
+    match raw.get("schema_version") {
+        None => UNVERSIONED_SCHEMA_VERSION,
+        Some(toml::Value::Integer(version)) if *version >= 1 => {
+            u32::try_from(*version).unwrap_or(u32::MAX)
+        }
+        Some(other) => {
+            issues.push(SettingsValidationIssue {
+                key: "schema_version".into(),
+                message: format!(
+                    "invalid schema version {}, treated as {}",
+                    other, UNVERSIONED_SCHEMA_VERSION
+                ),
+            });
+            UNVERSIONED_SCHEMA_VERSION
+        }
+    }
+}
+
+/// Apply every migration from `from` up to [`CURRENT_SCHEMA_VERSION`]
+pub fn migrate(raw: &mut toml::Table, from: u32) {
+    for (version, step) in MIGRATIONS {
+        if *version >= from && *version < CURRENT_SCHEMA_VERSION {
+            step(raw);
+        }
+    }
+}
+
+/// Values in `raw` that deserializing into [`Settings`] ignored, keyed by
+/// their path: unknown keys inside known sections. Unknown top-level keys
+/// are kept in `Settings::extra` and values reset as invalid are reported
+/// in `issues`, so neither is collected.
+fn unknown_nested_keys(
+    raw: &toml::Table,
+    settings: &Settings,
+    issues: &[SettingsValidationIssue],
+) -> toml::Table {
+    let Ok(serialized) = toml::Table::try_from(settings) else {
+        return toml::Table::new();
+    };
+    let mut unknown = toml::Table::new();
+    for (section, value) in raw {
+        if let (Some(fields), Some(known)) = (
+            value.as_table(),
+            serialized.get(section).and_then(|v| v.as_table()),
+        ) {
+            collect_unknown(&serialized, &[section], fields, known, issues, &mut unknown);
+        }
+    }
+    unknown
+}
+
+fn collect_unknown(
+    serialized: &toml::Table,
+    path: &[&str],
+    fields: &toml::Table,
+    known: &toml::Table,
+    issues: &[SettingsValidationIssue],
+    unknown: &mut toml::Table,
+) {
+    for (key, value) in fields {
+        let mut key_path = path.to_vec();
+        key_path.push(key);
+        match known.get(key) {
+            Some(toml::Value::Table(known)) => {
+                if let Some(fields) = value.as_table() {
+                    collect_unknown(serialized, &key_path, fields, known, issues, unknown);
+                }
+            }
+            Some(_) => {}
+            None => {
+                let dotted = key_path.join(".");
+                if issues.iter().any(|issue| issue.key == dotted) {
+                    continue;
+                }
+                // A key is unknown when putting it back changes nothing;
+                // a known field that serializes to nothing (an empty
+                // list, say) is harmless to keep either way.
+                let mut probe = serialized.clone();
+                insert_at(&mut probe, &key_path, value.clone());
+                let ignored = try_settings(&probe)
+                    .ok()
+                    .and_then(|settings| toml::Table::try_from(&settings).ok())
+                    .is_some_and(|reserialized| reserialized == *serialized);
+                if ignored {
+                    insert_at(unknown, &key_path, value.clone());
+                }
+            }
+        }
+    }
+}
+
+/// Set `path` in `table`, creating intermediate tables as needed
+fn insert_at(table: &mut toml::Table, path: &[&str], value: toml::Value) {
+    let Some((last, parents)) = path.split_last() else {
+        return;
+    };
+    let mut current = table;
+    for parent in parents {
+        let Some(next) = current
+            .entry(parent.to_string())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+            .as_table_mut()
+        else {
+            return;
+        };
+        current = next;
+    }
+    current.insert(last.to_string(), value);
+}
+
+/// Copy every key of `extra` that `table` does not already have
+fn merge_missing(table: &mut toml::Table, extra: &toml::Table) {
+    for (key, value) in extra {
+        match (table.get_mut(key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(nested)) => {
+                merge_missing(existing, nested)
+            }
+            (Some(_), _) => {}
+            (None, _) => {
+                table.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+fn section_table<'a>(raw: &'a mut toml::Table, section: &str) -> Option<&'a mut toml::Table> {
+    raw.get_mut(section).and_then(|value| value.as_table_mut())
+}
+
+fn try_settings(raw: &toml::Table) -> Result<Settings, toml::de::Error> {
+    toml::Value::Table(raw.clone()).try_into()
+}
+
+/// Deserialize `raw`, dropping values that do not fit their field. Each
+/// dropped value is reported and falls back to its default.
+fn deserialize_lenient(
+    mut raw: toml::Table,
+    issues: &mut Vec<SettingsValidationIssue>,
+) -> Settings {
+    if let Ok(settings) = try_settings(&raw) {
+        return settings;
+    }
+
+    // Invalid tray entries are repaired item by item rather than reset
+    if Settings::sanitize_tray_fields(&mut raw) {
+        if let Ok(settings) = try_settings(&raw) {
+            return settings;
+        }
+    }
+
+    // Rebuild the table one value at a time, keeping whatever still
+    // deserializes. Only reached for broken files, so the quadratic cost
+    // does not matter.
+    let mut accepted = toml::Table::new();
+    for (section, value) in raw {
+        let toml::Value::Table(fields) = value else {
+            accepted.insert(section.clone(), value);
+            if let Err(e) = try_settings(&accepted) {
+                accepted.remove(&section);
+                issues.push(SettingsValidationIssue {
+                    key: section,
+                    message: e.message().to_string(),
+                });
+            }
+            continue;
+        };
+
+        accepted.insert(section.clone(), toml::Value::Table(toml::Table::new()));
+        if let Err(e) = try_settings(&accepted) {
+            accepted.remove(&section);
+            issues.push(SettingsValidationIssue {
+                key: section,
+                message: e.message().to_string(),
+            });
+            continue;
+        }
+        for (field, value) in fields {
+            if let Some(table) = section_table(&mut accepted, &section) {
+                table.insert(field.clone(), value);
+            }
+            if let Err(e) = try_settings(&accepted) {
+                if let Some(table) = section_table(&mut accepted, &section) {
+                    table.remove(&field);
+                }
+                issues.push(SettingsValidationIssue {
+                    key: format!("{}.{}", section, field),
+                    message: e.message().to_string(),
+                });
+            }
+        }
+    }
+
+    try_settings(&accepted).unwrap_or_default()
+}
+
+impl Settings {
+    /// Parse a config file, upgrading it to [`CURRENT_SCHEMA_VERSION`].
+    /// Only a file that is not valid TOML at all is an error.
+    pub fn parse_versioned(content: &str) -> CogniaResult<(Self, SettingsLoadReport)> {
+        let mut raw: toml::Table = toml::from_str(content)
+            .map_err(|e| CogniaError::Parse(format!("Failed to parse config: {}", e)))?;
+
+        let mut issues = Vec::new();
+        let from_version = schema_version(&raw, &mut issues);
+        let to_version = from_version.max(CURRENT_SCHEMA_VERSION);
+        if from_version > CURRENT_SCHEMA_VERSION {
+            issues.push(SettingsValidationIssue {
+                key: "schema_version".into(),
+                message: format!(
+                    "written by a newer release (v{}, this release supports v{}); unknown settings are kept but ignored",
+                    from_version, CURRENT_SCHEMA_VERSION
+                ),
+            });
+        }
+        let before = raw.clone();
+        migrate(&mut raw, from_version);
+        // Migrations that changed nothing leave a file that is already in
+        // the current layout, so there is nothing to back up or report
+        let from_version = if raw == before {
+            from_version.max(to_version)
+        } else {
+            from_version
+        };
+        raw.insert(
+            "schema_version".into(),
+            toml::Value::Integer(i64::from(to_version)),
+        );
+
+        let mut settings = deserialize_lenient(raw.clone(), &mut issues);
+        settings.nested_extra = unknown_nested_keys(&raw, &settings, &issues);
+
+        Ok((
+            settings,
+            SettingsLoadReport {
+                from_version,
+                to_version,
+                issues,
+                backup_path: None,
+            },
+        ))
+    }
+
+    /// Serialize for writing to `config.toml`, putting back the unknown
+    /// keys of known sections that were read from it
+    pub fn to_toml_string(&self) -> Result<String, toml::ser::Error> {
+        if self.nested_extra.is_empty() {
+            return toml::to_string_pretty(self);
+        }
+        let mut table = toml::Table::try_from(self)?;
+        merge_missing(&mut table, &self.nested_extra);
+        toml::to_string_pretty(&table)
+    }
+
+    /// Where the original file is copied before it is rewritten, e.g.
+    /// `config.toml.v1.bak`
+    pub fn backup_path_for(path: &Path, tag: &str) -> PathBuf {
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "config.toml".into());
+        path.with_file_name(format!("{}.{}.bak", file_name, tag))
+    }
+
+    /// Load the config file like [`Settings::load`], then persist any
+    /// migration or reset values. The original file is backed up first.
+    /// A file that cannot be parsed at all is backed up and replaced by
+    /// defaults in memory; it is only overwritten on the next save.
+    pub async fn load_and_migrate() -> CogniaResult<(Self, SettingsLoadReport)> {
+        let path = Self::config_path()
+            .ok_or_else(|| CogniaError::Config("Could not determine config path".into()))?;
+
+        if !fs::exists(&path).await {
+            return Ok((
+                Self::default(),
+                SettingsLoadReport::clean(CURRENT_SCHEMA_VERSION),
+            ));
+        }
+
+        let content = fs::read_file_string(&path).await?;
+        let (settings, mut report) = match Self::parse_versioned(&content) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                let backup = Self::backup_path_for(&path, "invalid");
+                fs::write_file_atomic(&backup, content.as_bytes()).await?;
+                let mut report = SettingsLoadReport::clean(CURRENT_SCHEMA_VERSION);
+                report.issues.push(SettingsValidationIssue {
+                    key: String::new(),
+                    message: e.to_string(),
+                });
+                report.backup_path = Some(backup.display().to_string());
+                return Ok((Self::default(), report));
+            }
+        };
+
+        // Never rewrite a file from a newer release, it would lose data
+        if report.is_clean() || report.from_version > CURRENT_SCHEMA_VERSION {
+            return Ok((settings, report));
+        }
+
+        let backup = Self::backup_path_for(&path, &format!("v{}", report.from_version));
+        fs::write_file_atomic(&backup, content.as_bytes()).await?;
+        settings.save_to(&path).await?;
+        report.backup_path = Some(backup.display().to_string());
+        log::info!(
+            "Config migrated from v{} to v{} ({} value(s) reset), backup at {}",
+            report.from_version,
+            report.to_version,
+            report.issues.len(),
+            backup.display()
+        );
+
+        Ok((settings, report))
+    }
+}
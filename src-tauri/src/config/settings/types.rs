@@ -1,3 +1,10 @@
+use crate::core::archive::SymlinkMode;
+use crate::core::notifications::{NotificationCategory, NotificationDelivery};
+use crate::core::onboarding::OnboardingStepRecord;
+use crate::core::pin_file::PinFileFormat;
+use crate::core::profiles::DEFAULT_SECRET_KEY_PATTERNS;
+use crate::core::python_tools::PythonToolBackendChoice;
+use crate::platform::env::{Architecture, Platform};
 use crate::tray::{
     TrayClickBehavior, TrayMenuItemId, TrayNotificationEvent, TrayNotificationLevel,
     TrayQuickAction,
@@ -6,9 +13,11 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Settings {
+    /// Layout version of the config file, see `CURRENT_SCHEMA_VERSION`
+    pub schema_version: u32,
     pub general: GeneralSettings,
     pub network: NetworkSettings,
     pub mirrors: HashMap<String, MirrorConfig>,
@@ -26,6 +35,46 @@ pub struct Settings {
     pub plugin: PluginSettings,
     pub startup: StartupSettings,
     pub shortcuts: ShortcutSettings,
+    pub notifications: NotificationSettings,
+    /// First-run setup progress on this machine
+    pub onboarding: OnboardingSettings,
+    /// Top-level tables this version does not know about, kept so they
+    /// survive a save (e.g. settings written by a newer release)
+    #[serde(flatten)]
+    pub extra: HashMap<String, toml::Value>,
+    /// Keys inside known sections this version does not know about, at any
+    /// depth, merged back in by `to_toml_string` so they survive a save
+    #[serde(skip)]
+    pub nested_extra: toml::Table,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            schema_version: super::CURRENT_SCHEMA_VERSION,
+            general: GeneralSettings::default(),
+            network: NetworkSettings::default(),
+            mirrors: HashMap::new(),
+            providers: HashMap::new(),
+            paths: PathSettings::default(),
+            security: SecuritySettings::default(),
+            provider_settings: GlobalProviderSettings::default(),
+            appearance: AppearanceSettings::default(),
+            updates: UpdateSettings::default(),
+            tray: TraySettings::default(),
+            envvar: EnvVarSettings::default(),
+            terminal: TerminalSettings::default(),
+            log: LogSettings::default(),
+            backup: BackupSettings::default(),
+            plugin: PluginSettings::default(),
+            startup: StartupSettings::default(),
+            shortcuts: ShortcutSettings::default(),
+            notifications: NotificationSettings::default(),
+            onboarding: OnboardingSettings::default(),
+            extra: HashMap::new(),
+            nested_extra: toml::Table::new(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,6 +92,8 @@ pub struct UpdateSettings {
     pub custom_endpoints: Vec<String>,
     /// Whether to retry official source when selected source fails
     pub fallback_to_official: bool,
+    /// Release channel the self-updater follows
+    pub update_channel: UpdateChannel,
 }
 
 impl Default for UpdateSettings {
@@ -54,6 +105,7 @@ fn default() -> Self {
             source_mode: UpdateSourceMode::Official,
             custom_endpoints: Vec::new(),
             fallback_to_official: true,
+            update_channel: UpdateChannel::Stable,
         }
     }
 }
@@ -72,6 +124,30 @@ fn default() -> Self {
     }
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    Stable,
+    Beta,
+    Nightly,
+}
+
+impl Default for UpdateChannel {
+    fn default() -> Self {
+        Self::Stable
+    }
+}
+
+impl UpdateChannel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Stable => "stable",
+            Self::Beta => "beta",
+            Self::Nightly => "nightly",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct TraySettings {
@@ -128,6 +204,9 @@ pub struct EnvVarSettings {
     pub default_scope: String,
     pub auto_snapshot: bool,
     pub mask_sensitive: bool,
+    /// Case-insensitive substrings marking an env var key as a secret, so its
+    /// value is redacted from exported profiles
+    pub secret_key_patterns: Vec<String>,
 }
 
 impl Default for EnvVarSettings {
@@ -136,6 +215,10 @@ fn default() -> Self {
             default_scope: "all".to_string(),
             auto_snapshot: false,
             mask_sensitive: true,
+            secret_key_patterns: DEFAULT_SECRET_KEY_PATTERNS
+                .iter()
+                .map(|pattern| pattern.to_string())
+                .collect(),
         }
     }
 }
@@ -175,6 +258,15 @@ pub struct PluginSettings {
     pub sandbox_fs: bool,
     /// Permission enforcement mode for plugins: "compat" or "strict".
     pub permission_enforcement_mode: String,
+    /// URL of the signed marketplace index; empty disables remote marketplace.
+    pub marketplace_index_url: String,
+    /// Hex-encoded ed25519 public keys trusted to sign the marketplace index
+    /// (JSON array in config layer).
+    pub marketplace_trusted_keys: Vec<String>,
+    /// Allow installing from unsigned marketplace indexes.
+    pub developer_mode: bool,
+    /// Per-plugin key-value storage quota in megabytes.
+    pub storage_quota_mb: u64,
 }
 
 impl Default for PluginSettings {
@@ -184,6 +276,10 @@ fn default() -> Self {
             max_execution_timeout_secs: 30,
             sandbox_fs: true,
             permission_enforcement_mode: "compat".to_string(),
+            marketplace_index_url: String::new(),
+            marketplace_trusted_keys: Vec::new(),
+            developer_mode: false,
+            storage_quota_mb: crate::plugin::storage::DEFAULT_STORAGE_QUOTA_MB,
         }
     }
 }
@@ -212,6 +308,60 @@ fn default() -> Self {
     }
 }
 
+/// Progress of the first-run setup, one record per step. This is machine
+/// state: config export leaves it out and import keeps the local one.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct OnboardingSettings {
+    pub root_dir: OnboardingStepRecord,
+    pub mirrors: OnboardingStepRecord,
+    pub path_setup: OnboardingStepRecord,
+    pub env_detect: OnboardingStepRecord,
+    pub feedback: OnboardingStepRecord,
+    /// Shells whose profile or PATH the setup configured
+    pub configured_shells: Vec<String>,
+    /// Whether the user agreed to send feedback and diagnostics
+    pub feedback_opt_in: bool,
+}
+
+/// Delivery preference per notification category
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct NotificationSettings {
+    /// Download completed, failed or monthly cap exceeded
+    pub download: NotificationDelivery,
+    /// Environment or app updates available
+    pub update: NotificationDelivery,
+    /// Installed versions past or near end-of-life
+    pub eol: NotificationDelivery,
+    /// Install jobs that failed
+    pub install_failure: NotificationDelivery,
+    /// Everything else, including notifications sent by the frontend
+    pub system: NotificationDelivery,
+}
+
+impl NotificationSettings {
+    pub fn delivery_for(&self, category: NotificationCategory) -> NotificationDelivery {
+        match category {
+            NotificationCategory::Download => self.download,
+            NotificationCategory::Update => self.update,
+            NotificationCategory::Eol => self.eol,
+            NotificationCategory::InstallFailure => self.install_failure,
+            NotificationCategory::System => self.system,
+        }
+    }
+
+    pub fn set_delivery(&mut self, category: NotificationCategory, delivery: NotificationDelivery) {
+        match category {
+            NotificationCategory::Download => self.download = delivery,
+            NotificationCategory::Update => self.update = delivery,
+            NotificationCategory::Eol => self.eol = delivery,
+            NotificationCategory::InstallFailure => self.install_failure = delivery,
+            NotificationCategory::System => self.system = delivery,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct AppearanceSettings {
@@ -249,6 +399,17 @@ pub struct TerminalSettings {
     pub proxy_mode: String,
     pub custom_proxy: Option<String>,
     pub no_proxy: Option<String>,
+    /// Maximum number of concurrent interactive PTY sessions
+    pub max_sessions: u32,
+    /// Unpinned entries kept in the profile launch history
+    pub launch_history_max: u32,
+    /// Store env var values in the launch history instead of only their names
+    pub launch_history_record_env_values: bool,
+    /// Seconds between resource samples of processes started via the launch commands
+    pub launch_sample_interval_secs: u32,
+    /// Kill launched processes when the app exits instead of leaving them
+    /// running untracked
+    pub terminate_launches_on_exit: bool,
 }
 
 impl Default for TerminalSettings {
@@ -260,6 +421,11 @@ fn default() -> Self {
             proxy_mode: "global".into(),
             custom_proxy: None,
             no_proxy: None,
+            max_sessions: 8,
+            launch_history_max: 100,
+            launch_history_record_env_values: false,
+            launch_sample_interval_secs: 2,
+            terminate_launches_on_exit: false,
         }
     }
 }
@@ -330,6 +496,47 @@ pub struct CustomCacheEntry {
     pub category: String,
 }
 
+/// User-defined release asset matching rule. Rules are tried in order and a
+/// match takes precedence over the built-in platform/arch heuristics.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetMatchRule {
+    /// Platform the rule applies to; `None` applies to every platform
+    #[serde(default)]
+    pub platform: Option<Platform>,
+    /// Architecture the rule applies to; `None` applies to every architecture
+    #[serde(default)]
+    pub arch: Option<Architecture>,
+    /// Case-insensitive regex matched against the asset file name
+    pub pattern: String,
+}
+
+/// User-defined post-install smoke test, run after the built-in checks for
+/// its environment type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SmokeTestDefinition {
+    /// Environment type the test applies to (e.g. `node`, `python`)
+    pub env_type: String,
+    /// Label shown in results; defaults to the command line
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Program to run, resolved on the installed version's PATH
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Substring that stdout or stderr must contain
+    #[serde(default)]
+    pub expected_output: Option<String>,
+    /// Exit code the command must return; `None` expects 0
+    #[serde(default)]
+    pub expected_exit_code: Option<i32>,
+    /// Command line the user approved. Commands that are not allowlisted only
+    /// run while this matches them
+    #[serde(default)]
+    pub approved_command: Option<String>,
+}
+
 /// Named presets for scan aggressiveness (mirrors `cache::external::ScanPreset`).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -409,6 +616,8 @@ pub fn to_scan_config(&self) -> crate::cache::external::ScanConfig {
 #[serde(default)]
 pub struct GeneralSettings {
     pub parallel_downloads: u32,
+    /// Environment installs a batch runs at the same time (1-8)
+    pub parallel_env_installs: u32,
     pub resolve_strategy: ResolveStrategy,
     pub auto_update_metadata: bool,
     pub metadata_cache_ttl: u64,
@@ -424,10 +633,46 @@ pub struct GeneralSettings {
     pub cache_monitor_interval: u64,
     /// Whether to include external caches in size monitoring
     pub cache_monitor_external: bool,
+    /// Send a notification each month summarizing reclaimed cache space
+    pub cache_monthly_summary: bool,
     /// Download speed limit in bytes/sec (0 = unlimited)
     pub download_speed_limit: u64,
+    /// Monthly download soft cap in MB; exceeding it only notifies (0 = disabled)
+    pub download_monthly_cap_mb: u64,
+    /// Drop download history records older than this many days (0 = keep forever)
+    pub download_history_max_age_days: u32,
+    /// Maximum number of download history records to keep (10-10000, default 1000)
+    pub download_history_max_records: usize,
+    /// Purge trashed profiles, rules and shims after this many days (0 = keep forever)
+    pub trash_retention_days: u32,
+    /// Resume downloads restored from the previous session instead of leaving them paused
+    pub download_auto_resume: bool,
+    /// Minimum gap in milliseconds between progress events sent to the UI
+    /// (16-5000); applied when the download manager starts
+    pub progress_event_interval_ms: u64,
+    /// How symlinks in extracted archives are created: native, junction
+    /// (directory links on Windows) or copy
+    pub extract_symlink_mode: SymlinkMode,
+    /// File `env_use_local` writes pins to: native (the provider's own
+    /// file), tool_versions or mise
+    pub pin_file_format: PinFileFormat,
+    /// Pin file format last chosen for each project, keyed by project path;
+    /// overrides `pin_file_format` there
+    #[serde(default)]
+    pub project_pin_file_formats: HashMap<String, PinFileFormat>,
+    /// Backend for isolated Python tool installs: auto (uv, else pipx),
+    /// pipx or uv
+    pub python_tool_backend: PythonToolBackendChoice,
     /// Max concurrent tasks for update checking (1-32, default 8)
     pub update_check_concurrency: u32,
+    /// Serve Prometheus metrics at `http://127.0.0.1:<port>/metrics`
+    pub metrics_listener_enabled: bool,
+    /// Loopback port of the metrics listener (1024-65535)
+    pub metrics_listener_port: u16,
+    /// Serve the read-only JSON-RPC API on a user-only socket / named pipe
+    pub local_api_enabled: bool,
+    /// Calls allowed per API method per minute (1-10000)
+    pub local_api_rate_limit_per_minute: u32,
     /// External cache provider IDs to exclude from scanning (e.g. ["gradle","maven"])
     #[serde(default)]
     pub external_cache_excluded_providers: Vec<String>,
@@ -437,12 +682,22 @@ pub struct GeneralSettings {
     /// Configurable scan parameters and presets
     #[serde(default)]
     pub cache_scan_settings: CacheScanSettings,
+    /// Ordered release asset rules tried before the built-in heuristics
+    #[serde(default)]
+    pub asset_match_rules: Vec<AssetMatchRule>,
+    /// Regexes for release assets that are never picked (e.g. `-debug`)
+    #[serde(default)]
+    pub asset_exclude_patterns: Vec<String>,
+    /// Extra smoke tests run after installing an environment version
+    #[serde(default)]
+    pub smoke_tests: Vec<SmokeTestDefinition>,
 }
 
 impl Default for GeneralSettings {
     fn default() -> Self {
         Self {
             parallel_downloads: 4,
+            parallel_env_installs: 2,
             resolve_strategy: ResolveStrategy::Latest,
             auto_update_metadata: true,
             metadata_cache_ttl: 3600,
@@ -454,11 +709,29 @@ fn default() -> Self {
             cache_auto_clean_threshold: 80,
             cache_monitor_interval: 300, // 5 minutes
             cache_monitor_external: false,
+            cache_monthly_summary: false,
             download_speed_limit: 0,
+            download_monthly_cap_mb: 0,
+            download_history_max_age_days: 0,
+            download_history_max_records: 1000,
+            trash_retention_days: crate::core::trash::DEFAULT_TRASH_RETENTION_DAYS,
+            download_auto_resume: true,
+            progress_event_interval_ms: crate::download::DEFAULT_PROGRESS_INTERVAL_MS,
+            extract_symlink_mode: SymlinkMode::default(),
+            pin_file_format: PinFileFormat::default(),
+            project_pin_file_formats: HashMap::new(),
+            python_tool_backend: PythonToolBackendChoice::default(),
             update_check_concurrency: 8,
+            metrics_listener_enabled: false,
+            metrics_listener_port: crate::core::metrics::DEFAULT_METRICS_PORT,
+            local_api_enabled: false,
+            local_api_rate_limit_per_minute: crate::core::local_api::DEFAULT_RATE_LIMIT_PER_MINUTE,
             external_cache_excluded_providers: Vec::new(),
             custom_cache_entries: Vec::new(),
             cache_scan_settings: CacheScanSettings::default(),
+            asset_match_rules: Vec::new(),
+            asset_exclude_patterns: Vec::new(),
+            smoke_tests: Vec::new(),
         }
     }
 }
@@ -485,6 +758,11 @@ pub struct NetworkSettings {
     pub retries: u32,
     pub proxy: Option<String>,
     pub no_proxy: Option<String>,
+    /// Per-provider proxy overrides keyed by provider id (`github`, `npm`, `pypi`, ...).
+    /// A value of `direct` bypasses the global proxy for that provider.
+    pub provider_proxies: HashMap<String, String>,
+    /// Serve package search/info from the metadata cache only, never touching the network.
+    pub offline_mode: bool,
 }
 
 impl Default for NetworkSettings {
@@ -494,6 +772,8 @@ fn default() -> Self {
             retries: 3,
             proxy: None,
             no_proxy: None,
+            provider_proxies: HashMap::new(),
+            offline_mode: false,
         }
     }
 }
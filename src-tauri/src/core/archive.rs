@@ -0,0 +1,1408 @@
+//! Archive extraction shared by the download manager and the env installers.
+//!
+//! Every entry path is checked before anything is written: entries with `..`,
+//! absolute or drive-prefixed paths fail the whole extraction. Symlinks and
+//! hard links are created after all regular entries, and only when their
+//! target stays inside the destination. On Windows, where creating symlinks
+//! usually needs elevation, [`SymlinkMode`] decides whether links become
+//! junctions or copies, and all file IO goes through `\\?\` paths so deep
+//! trees (node headers, JDK sources) do not hit `MAX_PATH`.
+
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::{fs, process};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+pub type ProgressCallback = Arc<dyn Fn(ExtractProgress) + Send + Sync>;
+
+/// External 7-Zip binaries tried before the bundled decoder
+const SEVEN_ZIP_BINARIES: &[&str] = &["7z", "7zz", "7za"];
+
+const SEVEN_ZIP_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Symlink mode from settings, used when no mode is passed explicitly
+static SYMLINK_MODE: Lazy<RwLock<SymlinkMode>> = Lazy::new(|| RwLock::new(SymlinkMode::default()));
+
+// ── Options ──
+
+/// How symlinks found in archives are materialized
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SymlinkMode {
+    /// Real symlinks. On Windows this falls back to a copy when the process
+    /// may not create symlinks.
+    Native,
+    /// Junctions for directory links on Windows, copies for file links.
+    /// Same as `Native` elsewhere.
+    Junction,
+    /// Copy the link target on every platform
+    Copy,
+}
+
+impl Default for SymlinkMode {
+    fn default() -> Self {
+        Self::Junction
+    }
+}
+
+impl std::fmt::Display for SymlinkMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Native => "native",
+            Self::Junction => "junction",
+            Self::Copy => "copy",
+        })
+    }
+}
+
+impl std::str::FromStr for SymlinkMode {
+    type Err = CogniaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "native" => Ok(Self::Native),
+            "junction" => Ok(Self::Junction),
+            "copy" => Ok(Self::Copy),
+            _ => Err(CogniaError::Config(
+                "Invalid symlink mode. Valid: native, junction, copy".into(),
+            )),
+        }
+    }
+}
+
+/// Replace the symlink mode used by [`ExtractOptions::default`]
+pub fn set_symlink_mode(mode: SymlinkMode) {
+    if let Ok(mut guard) = SYMLINK_MODE.write() {
+        *guard = mode;
+    }
+}
+
+/// Currently configured symlink mode
+pub fn symlink_mode() -> SymlinkMode {
+    SYMLINK_MODE.read().map(|guard| *guard).unwrap_or_default()
+}
+
+#[derive(Clone)]
+pub struct ExtractOptions {
+    pub symlink_mode: SymlinkMode,
+    pub on_progress: Option<ProgressCallback>,
+}
+
+impl Default for ExtractOptions {
+    /// Configured symlink mode, no progress reporting
+    fn default() -> Self {
+        Self {
+            symlink_mode: symlink_mode(),
+            on_progress: None,
+        }
+    }
+}
+
+// ── Extraction progress ──
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractProgress {
+    pub current_file: String,
+    pub files_done: usize,
+    /// Known up front for zip archives only
+    pub total_files: Option<usize>,
+    /// Bytes of file content written so far
+    pub bytes_written: u64,
+}
+
+/// Wrap `callback` so it fires at most once per `interval`. Extracting a
+/// toolchain produces thousands of entries; forwarding each one to the
+/// frontend would flood the event channel.
+pub fn throttle_progress(
+    interval: Duration,
+    callback: impl Fn(ExtractProgress) + Send + Sync + 'static,
+) -> ProgressCallback {
+    let last: Mutex<Option<Instant>> = Mutex::new(None);
+    Arc::new(move |progress: ExtractProgress| {
+        let Ok(mut last) = last.lock() else {
+            return;
+        };
+        let done = progress.total_files == Some(progress.files_done);
+        if done || last.map_or(true, |t| t.elapsed() >= interval) {
+            *last = Some(Instant::now());
+            callback(progress);
+        }
+    })
+}
+
+struct ProgressTracker<'a> {
+    callback: &'a Option<ProgressCallback>,
+    files_done: usize,
+    total_files: Option<usize>,
+    bytes_written: u64,
+}
+
+impl<'a> ProgressTracker<'a> {
+    fn new(callback: &'a Option<ProgressCallback>, total_files: Option<usize>) -> Self {
+        Self {
+            callback,
+            files_done: 0,
+            total_files,
+            bytes_written: 0,
+        }
+    }
+
+    fn entry_done(&mut self, current_file: &Path, bytes: u64) {
+        self.files_done += 1;
+        self.bytes_written += bytes;
+        if let Some(cb) = self.callback {
+            cb(ExtractProgress {
+                current_file: current_file.display().to_string(),
+                files_done: self.files_done,
+                total_files: self.total_files,
+                bytes_written: self.bytes_written,
+            });
+        }
+    }
+}
+
+// ── Paths ──
+
+/// Resolve `.` and `..` without touching the filesystem
+pub fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !out.pop() {
+                    out.push("..");
+                }
+            }
+            other => out.push(other.as_os_str()),
+        }
+    }
+    out
+}
+
+/// Extended-length form of an absolute path (`\\?\C:\…`, `\\?\UNC\…`) so
+/// Win32 calls accept paths longer than `MAX_PATH`. The path must already be
+/// normalized with backslash separators; verbatim paths are not parsed.
+#[cfg(windows)]
+pub fn long_path(path: &Path) -> PathBuf {
+    let Some(s) = path.to_str() else {
+        return path.to_path_buf();
+    };
+    if s.starts_with(r"\\?\") || !path.is_absolute() {
+        return path.to_path_buf();
+    }
+    match s.strip_prefix(r"\\") {
+        Some(unc) => PathBuf::from(format!(r"\\?\UNC\{}", unc)),
+        None => PathBuf::from(format!(r"\\?\{}", s)),
+    }
+}
+
+#[cfg(not(windows))]
+pub fn long_path(path: &Path) -> PathBuf {
+    path.to_path_buf()
+}
+
+fn absolute_dest(dest: &Path) -> CogniaResult<PathBuf> {
+    let absolute = if dest.is_absolute() {
+        dest.to_path_buf()
+    } else {
+        std::env::current_dir()?.join(dest)
+    };
+    Ok(normalize_lexically(&absolute))
+}
+
+/// Join an archive entry path onto `dest`, rejecting anything that could
+/// land outside of it
+fn validate_extract_path(dest: &Path, entry_path: &Path) -> CogniaResult<PathBuf> {
+    let mut target = dest.to_path_buf();
+    for component in entry_path.components() {
+        match component {
+            Component::Normal(part) => target.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                return Err(CogniaError::Installation(format!(
+                    "Zip-slip detected: path contains '..': {}",
+                    entry_path.display()
+                )));
+            }
+            Component::RootDir | Component::Prefix(_) => {
+                return Err(CogniaError::Installation(format!(
+                    "Zip-slip detected: {} escapes destination {}",
+                    entry_path.display(),
+                    dest.display()
+                )));
+            }
+        }
+    }
+    Ok(target)
+}
+
+fn extract_error(action: &str, path: &Path, e: impl std::fmt::Display) -> CogniaError {
+    CogniaError::Installation(format!("Failed to {} {}: {}", action, path.display(), e))
+}
+
+fn create_parent_dirs(target: &Path) -> CogniaResult<()> {
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(long_path(parent))
+            .map_err(|e| extract_error("create parent dir", parent, e))?;
+    }
+    Ok(())
+}
+
+// ── Links ──
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LinkKind {
+    Symbolic,
+    Hard,
+}
+
+/// A link entry, created once all regular entries are on disk
+#[derive(Debug)]
+struct PendingLink {
+    kind: LinkKind,
+    path: PathBuf,
+    /// Target as stored in the archive
+    target: PathBuf,
+    /// Absolute target inside the destination
+    resolved: PathBuf,
+}
+
+impl PendingLink {
+    /// Returns `None` (and logs) when the link points outside `dest`
+    fn new(kind: LinkKind, dest: &Path, path: PathBuf, target: PathBuf) -> Option<Self> {
+        let resolved = match kind {
+            // Hard link targets are archive paths, relative to the root
+            LinkKind::Hard => validate_extract_path(dest, &target).ok(),
+            LinkKind::Symbolic if target.is_absolute() || target.has_root() => None,
+            LinkKind::Symbolic => {
+                let base = path.parent().unwrap_or(dest);
+                Some(normalize_lexically(&base.join(&target))).filter(|r| r.starts_with(dest))
+            }
+        };
+        match resolved {
+            Some(resolved) => Some(Self {
+                kind,
+                path,
+                target,
+                resolved,
+            }),
+            None => {
+                log::warn!(
+                    "Skipping link outside destination: {} -> {}",
+                    path.display(),
+                    target.display()
+                );
+                None
+            }
+        }
+    }
+}
+
+fn copy_recursive(src: &Path, dst: &Path) -> std::io::Result<()> {
+    if !src.is_dir() {
+        std::fs::copy(long_path(src), long_path(dst))?;
+        return Ok(());
+    }
+    for entry in walkdir::WalkDir::new(long_path(src)) {
+        let entry = entry?;
+        let rel = entry
+            .path()
+            .strip_prefix(long_path(src))
+            .map_err(std::io::Error::other)?;
+        let out = long_path(&dst.join(rel));
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&out)?;
+        } else {
+            std::fs::copy(entry.path(), &out)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn create_native_symlink(link: &PendingLink) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(&link.target, &link.path)
+}
+
+#[cfg(windows)]
+fn create_native_symlink(link: &PendingLink) -> std::io::Result<()> {
+    // Keep relative targets relative, with Windows separators
+    let target = normalize_lexically(&link.target);
+    if link.resolved.is_dir() {
+        std::os::windows::fs::symlink_dir(target, long_path(&link.path))
+    } else {
+        std::os::windows::fs::symlink_file(target, long_path(&link.path))
+    }
+}
+
+/// Create a directory junction by setting a mount-point reparse point
+/// directly; paths from the archive never pass through a shell
+#[cfg(windows)]
+fn create_junction(link: &PendingLink) -> std::io::Result<()> {
+    use std::os::windows::ffi::OsStrExt;
+    use std::os::windows::fs::OpenOptionsExt;
+    use std::os::windows::io::AsRawHandle;
+    use windows_sys::Win32::Storage::FileSystem::{
+        FILE_FLAG_BACKUP_SEMANTICS, FILE_FLAG_OPEN_REPARSE_POINT,
+    };
+    use windows_sys::Win32::System::IO::DeviceIoControl;
+
+    const FSCTL_SET_REPARSE_POINT: u32 = 0x0009_00A4;
+    const IO_REPARSE_TAG_MOUNT_POINT: u32 = 0xA000_0003;
+
+    let target = link
+        .resolved
+        .as_os_str()
+        .encode_wide()
+        .collect::<Vec<u16>>();
+    let substitute: Vec<u16> = r"\??\"
+        .encode_utf16()
+        .chain(target.iter().copied())
+        .collect();
+    let substitute_len = (substitute.len() * 2) as u16;
+    let print_len = (target.len() * 2) as u16;
+
+    // REPARSE_DATA_BUFFER with a MountPointReparseBuffer: both names are
+    // stored NUL-terminated, back to back
+    let mut path_buffer = Vec::with_capacity(substitute.len() + target.len() + 2);
+    path_buffer.extend_from_slice(&substitute);
+    path_buffer.push(0);
+    path_buffer.extend_from_slice(&target);
+    path_buffer.push(0);
+    let data_len = (8 + path_buffer.len() * 2) as u16;
+
+    let mut buffer: Vec<u8> = Vec::with_capacity(8 + data_len as usize);
+    buffer.extend_from_slice(&IO_REPARSE_TAG_MOUNT_POINT.to_le_bytes());
+    buffer.extend_from_slice(&data_len.to_le_bytes());
+    buffer.extend_from_slice(&0u16.to_le_bytes());
+    buffer.extend_from_slice(&0u16.to_le_bytes());
+    buffer.extend_from_slice(&substitute_len.to_le_bytes());
+    buffer.extend_from_slice(&(substitute_len + 2).to_le_bytes());
+    buffer.extend_from_slice(&print_len.to_le_bytes());
+    for unit in path_buffer {
+        buffer.extend_from_slice(&unit.to_le_bytes());
+    }
+
+    let io_path = long_path(&link.path);
+    std::fs::create_dir(&io_path)?;
+    let result = std::fs::OpenOptions::new()
+        .write(true)
+        .custom_flags(FILE_FLAG_BACKUP_SEMANTICS | FILE_FLAG_OPEN_REPARSE_POINT)
+        .open(&io_path)
+        .and_then(|dir| {
+            let mut returned = 0u32;
+            // SAFETY: `buffer` outlives the call and its length is passed alongside
+            let ok = unsafe {
+                DeviceIoControl(
+                    dir.as_raw_handle() as _,
+                    FSCTL_SET_REPARSE_POINT,
+                    buffer.as_ptr() as _,
+                    buffer.len() as u32,
+                    std::ptr::null_mut(),
+                    0,
+                    &mut returned,
+                    std::ptr::null_mut(),
+                )
+            };
+            if ok == 0 {
+                Err(std::io::Error::last_os_error())
+            } else {
+                Ok(())
+            }
+        });
+    if result.is_err() {
+        let _ = std::fs::remove_dir(&io_path);
+    }
+    result
+}
+
+fn create_link(link: &PendingLink, mode: SymlinkMode) -> std::io::Result<()> {
+    if !link.resolved.exists() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "link target does not exist yet",
+        ));
+    }
+    if link.kind == LinkKind::Hard {
+        return std::fs::hard_link(long_path(&link.resolved), long_path(&link.path))
+            .or_else(|_| copy_recursive(&link.resolved, &link.path));
+    }
+
+    match mode {
+        SymlinkMode::Copy => copy_recursive(&link.resolved, &link.path),
+        #[cfg(windows)]
+        SymlinkMode::Junction if link.resolved.is_dir() => create_junction(link),
+        #[cfg(windows)]
+        SymlinkMode::Junction => copy_recursive(&link.resolved, &link.path),
+        _ => create_native_symlink(link).or_else(|e| {
+            if cfg!(windows) {
+                log::debug!(
+                    "Symlink {} failed ({}), copying instead",
+                    link.path.display(),
+                    e
+                );
+                copy_recursive(&link.resolved, &link.path)
+            } else {
+                Err(e)
+            }
+        }),
+    }
+}
+
+/// The lexical target check cannot see through links created earlier
+/// (`a -> .` followed by `b -> a/..`), so check where the link really ends up
+fn link_stays_inside(path: &Path, canonical_dest: &Path) -> bool {
+    std::fs::canonicalize(long_path(path)).map_or(false, |real| real.starts_with(canonical_dest))
+}
+
+/// Create the deferred links. Links may point at other links, so failures
+/// are retried while each round still makes progress.
+fn create_links(
+    mut pending: Vec<PendingLink>,
+    dest: &Path,
+    mode: SymlinkMode,
+    files: &mut Vec<PathBuf>,
+    progress: &mut ProgressTracker<'_>,
+) -> CogniaResult<()> {
+    if pending.is_empty() {
+        return Ok(());
+    }
+    let canonical_dest =
+        std::fs::canonicalize(long_path(dest)).map_err(|e| extract_error("resolve", dest, e))?;
+
+    while !pending.is_empty() {
+        let before = pending.len();
+        let mut retry = Vec::new();
+        for link in pending {
+            create_parent_dirs(&link.path)?;
+            match create_link(&link, mode) {
+                Ok(()) if link_stays_inside(&link.path, &canonical_dest) => {
+                    progress.entry_done(&link.path, 0);
+                    files.push(link.path);
+                }
+                Ok(()) => {
+                    log::warn!(
+                        "Removing link that resolves outside destination: {} -> {}",
+                        link.path.display(),
+                        link.target.display()
+                    );
+                    let io_path = long_path(&link.path);
+                    let _ =
+                        std::fs::remove_file(&io_path).or_else(|_| std::fs::remove_dir(&io_path));
+                }
+                Err(e) => retry.push((link, e)),
+            }
+        }
+        if retry.len() == before {
+            for (link, e) in retry {
+                log::warn!(
+                    "Skipping link {} -> {}: {}",
+                    link.path.display(),
+                    link.target.display(),
+                    e
+                );
+            }
+            break;
+        }
+        pending = retry.into_iter().map(|(link, _)| link).collect();
+    }
+    Ok(())
+}
+
+// ── Public API ──
+
+pub async fn extract_archive(archive: &Path, dest: &Path) -> CogniaResult<Vec<PathBuf>> {
+    extract_archive_with_options(archive, dest, ExtractOptions::default()).await
+}
+
+pub async fn extract_archive_with_progress(
+    archive: &Path,
+    dest: &Path,
+    on_progress: Option<ProgressCallback>,
+) -> CogniaResult<Vec<PathBuf>> {
+    let options = ExtractOptions {
+        on_progress,
+        ..ExtractOptions::default()
+    };
+    extract_archive_with_options(archive, dest, options).await
+}
+
+pub async fn extract_archive_with_options(
+    archive: &Path,
+    dest: &Path,
+    options: ExtractOptions,
+) -> CogniaResult<Vec<PathBuf>> {
+    let filename = archive.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    let ext = archive.extension().and_then(|e| e.to_str()).unwrap_or("");
+
+    let dest = absolute_dest(dest)?;
+    fs::create_dir_all(long_path(&dest)).await?;
+
+    let format = match ext {
+        "gz" | "tgz" => TarCompression::Gzip,
+        "xz" | "txz" => {
+            if filename.ends_with(".tar.xz") || ext == "txz" {
+                TarCompression::Xz
+            } else {
+                return Err(CogniaError::Installation(format!(
+                    "Standalone .xz not supported, expected .tar.xz: {}",
+                    filename
+                )));
+            }
+        }
+        "bz2" | "tbz2" => TarCompression::Bzip2,
+        "zst" | "tzst" => TarCompression::Zstd,
+        "zip" => return extract_zip(archive, &dest, options).await,
+        "7z" => return extract_7z(archive, &dest, options).await,
+        _ => {
+            return Err(CogniaError::Installation(format!(
+                "Unsupported archive format: {}",
+                ext
+            )))
+        }
+    };
+
+    extract_tar(archive, &dest, format, options).await
+}
+
+// ── Format-specific extractors ──
+
+#[derive(Debug, Clone, Copy)]
+enum TarCompression {
+    Gzip,
+    Xz,
+    Bzip2,
+    Zstd,
+}
+
+fn extract_tar_entries<R: Read>(
+    mut archive: tar::Archive<R>,
+    dest: &Path,
+    options: &ExtractOptions,
+) -> CogniaResult<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut links = Vec::new();
+    let mut progress = ProgressTracker::new(&options.on_progress, None);
+    let entries = archive
+        .entries()
+        .map_err(|e| CogniaError::Installation(format!("Failed to read tar entries: {}", e)))?;
+
+    for entry in entries {
+        let mut entry = entry
+            .map_err(|e| CogniaError::Installation(format!("Failed to read tar entry: {}", e)))?;
+
+        let entry_path = entry
+            .path()
+            .map_err(|e| CogniaError::Installation(format!("Invalid path in tar: {}", e)))?
+            .into_owned();
+        let target = validate_extract_path(dest, &entry_path)?;
+
+        let entry_type = entry.header().entry_type();
+        if entry_type.is_symlink() || entry_type.is_hard_link() {
+            let kind = if entry_type.is_symlink() {
+                LinkKind::Symbolic
+            } else {
+                LinkKind::Hard
+            };
+            let link_target = entry
+                .link_name()
+                .map_err(|e| extract_error("read link target of", &entry_path, e))?;
+            if let Some(link_target) = link_target {
+                links.extend(PendingLink::new(
+                    kind,
+                    dest,
+                    target,
+                    link_target.into_owned(),
+                ));
+            }
+            continue;
+        }
+
+        create_parent_dirs(&target)?;
+        let bytes = if entry_type.is_file() {
+            entry.size()
+        } else {
+            0
+        };
+        entry
+            .unpack(long_path(&target))
+            .map_err(|e| extract_error("extract", &entry_path, e))?;
+
+        progress.entry_done(&entry_path, bytes);
+        files.push(target);
+    }
+
+    create_links(links, dest, options.symlink_mode, &mut files, &mut progress)?;
+    Ok(files)
+}
+
+async fn extract_tar(
+    archive: &Path,
+    dest: &Path,
+    compression: TarCompression,
+    options: ExtractOptions,
+) -> CogniaResult<Vec<PathBuf>> {
+    let archive = archive.to_path_buf();
+    let dest = dest.to_path_buf();
+
+    tokio::task::spawn_blocking(move || {
+        let file = std::fs::File::open(&archive).map_err(|e| extract_error("open", &archive, e))?;
+        let reader: Box<dyn Read> = match compression {
+            TarCompression::Gzip => Box::new(flate2::read::GzDecoder::new(file)),
+            TarCompression::Xz => Box::new(xz2::read::XzDecoder::new(file)),
+            TarCompression::Bzip2 => Box::new(bzip2::read::BzDecoder::new(file)),
+            TarCompression::Zstd => Box::new(zstd::Decoder::new(file).map_err(|e| {
+                CogniaError::Installation(format!("Failed to create zstd decoder: {}", e))
+            })?),
+        };
+        extract_tar_entries(tar::Archive::new(reader), &dest, &options)
+    })
+    .await
+    .map_err(|e| CogniaError::Installation(format!("Task join error: {}", e)))?
+}
+
+async fn extract_zip(
+    archive: &Path,
+    dest: &Path,
+    options: ExtractOptions,
+) -> CogniaResult<Vec<PathBuf>> {
+    let archive = archive.to_path_buf();
+    let dest = dest.to_path_buf();
+
+    tokio::task::spawn_blocking(move || {
+        let file = std::fs::File::open(&archive).map_err(|e| extract_error("open", &archive, e))?;
+        let mut zip = zip::ZipArchive::new(file)
+            .map_err(|e| CogniaError::Installation(format!("Failed to read zip archive: {}", e)))?;
+
+        let total = zip.len();
+        let mut files = Vec::with_capacity(total);
+        let mut links = Vec::new();
+        let mut progress = ProgressTracker::new(&options.on_progress, Some(total));
+
+        for i in 0..total {
+            let mut entry = zip.by_index(i).map_err(|e| {
+                CogniaError::Installation(format!("Failed to read zip entry {}: {}", i, e))
+            })?;
+
+            // Check the raw name rather than `enclosed_name()`, which would
+            // quietly drop a malicious entry instead of failing the archive
+            let entry_path = PathBuf::from(entry.name());
+            let target = validate_extract_path(&dest, &entry_path)?;
+
+            if entry.is_symlink() {
+                let mut link_target = String::new();
+                entry
+                    .read_to_string(&mut link_target)
+                    .map_err(|e| extract_error("read link target of", &entry_path, e))?;
+                links.extend(PendingLink::new(
+                    LinkKind::Symbolic,
+                    &dest,
+                    target,
+                    PathBuf::from(link_target),
+                ));
+                continue;
+            }
+
+            let mut bytes = 0;
+            if entry.is_dir() {
+                std::fs::create_dir_all(long_path(&target))
+                    .map_err(|e| extract_error("create dir", &target, e))?;
+            } else {
+                create_parent_dirs(&target)?;
+                let mut outfile = std::fs::File::create(long_path(&target))
+                    .map_err(|e| extract_error("create file", &target, e))?;
+                bytes = std::io::copy(&mut entry, &mut outfile)
+                    .map_err(|e| extract_error("write", &target, e))?;
+
+                // Preserve unix permissions
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::PermissionsExt;
+                    if let Some(mode) = entry.unix_mode() {
+                        let _ = std::fs::set_permissions(
+                            &target,
+                            std::fs::Permissions::from_mode(mode),
+                        );
+                    }
+                }
+            }
+
+            progress.entry_done(&entry_path, bytes);
+            files.push(target);
+        }
+
+        create_links(
+            links,
+            &dest,
+            options.symlink_mode,
+            &mut files,
+            &mut progress,
+        )?;
+        Ok(files)
+    })
+    .await
+    .map_err(|e| CogniaError::Installation(format!("Task join error: {}", e)))?
+}
+
+async fn find_seven_zip() -> Option<String> {
+    for name in SEVEN_ZIP_BINARIES {
+        if let Some(path) = process::which(name).await {
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// Validate every 7z entry against `dest` before anything is written.
+/// Returns the files the archive will create and their total size.
+fn plan_7z_entries(archive: &Path, dest: &Path) -> CogniaResult<(Vec<PathBuf>, u64)> {
+    let listing = sevenz_rust2::Archive::open(archive)
+        .map_err(|e| CogniaError::Installation(format!("Failed to read 7z archive: {}", e)))?;
+    let mut files = Vec::new();
+    let mut bytes = 0;
+    for entry in &listing.files {
+        let target = validate_extract_path(dest, Path::new(entry.name()))?;
+        if !entry.is_directory() && !entry.is_anti_item() {
+            bytes += entry.size();
+            files.push(target);
+        }
+    }
+    Ok((files, bytes))
+}
+
+/// Extract with 7-Zip when it is installed (faster, supports every 7z
+/// codec), otherwise with the bundled pure-Rust decoder
+async fn extract_7z(
+    archive: &Path,
+    dest: &Path,
+    options: ExtractOptions,
+) -> CogniaResult<Vec<PathBuf>> {
+    let (planned, bytes) = {
+        let archive = archive.to_path_buf();
+        let dest = dest.to_path_buf();
+        tokio::task::spawn_blocking(move || plan_7z_entries(&archive, &dest))
+            .await
+            .map_err(|e| CogniaError::Installation(format!("Task join error: {}", e)))??
+    };
+
+    let mut extracted = false;
+    if let Some(binary) = find_seven_zip().await {
+        let out_arg = format!("-o{}", dest.display());
+        let archive_arg = archive.display().to_string();
+        let args = ["x", "-y", out_arg.as_str(), archive_arg.as_str()];
+        match process::execute(
+            &binary,
+            &args,
+            Some(process::ProcessOptions::new().with_timeout(SEVEN_ZIP_TIMEOUT)),
+        )
+        .await
+        {
+            Ok(output) if output.success => extracted = true,
+            Ok(output) => log::warn!(
+                "{} failed on {}, using built-in decoder: {}",
+                binary,
+                archive.display(),
+                output.stderr.trim()
+            ),
+            Err(e) => log::warn!("Failed to run {}: {}", binary, e),
+        }
+    }
+
+    let archive = archive.to_path_buf();
+    let dest = dest.to_path_buf();
+
+    tokio::task::spawn_blocking(move || {
+        if !extracted {
+            sevenz_rust2::decompress_file_with_extract_fn(
+                &archive,
+                long_path(&dest),
+                |entry, reader, _| {
+                    let target = validate_extract_path(&dest, Path::new(entry.name()))
+                        .map_err(|e| sevenz_rust2::Error::Other(e.to_string().into()))?;
+                    sevenz_rust2::default_entry_extract_fn(entry, reader, &long_path(&target))
+                },
+            )
+            .map_err(|e| CogniaError::Installation(format!("Failed to extract 7z: {}", e)))?;
+        }
+
+        // 7-Zip restores symlinks stored in the archive; drop any that
+        // resolve outside the destination, like the zip/tar link checks
+        let canonical_dest = std::fs::canonicalize(long_path(&dest))
+            .map_err(|e| extract_error("resolve", &dest, e))?;
+        let files: Vec<PathBuf> = planned
+            .into_iter()
+            .filter(|path| {
+                let Ok(meta) = std::fs::symlink_metadata(long_path(path)) else {
+                    return false;
+                };
+                if meta.file_type().is_symlink() && !link_stays_inside(path, &canonical_dest) {
+                    log::warn!(
+                        "Removing link that resolves outside destination: {}",
+                        path.display()
+                    );
+                    let _ = std::fs::remove_file(long_path(path));
+                    return false;
+                }
+                true
+            })
+            .collect();
+
+        // Neither path reports per-entry progress; report the result
+        if let Some(cb) = &options.on_progress {
+            cb(ExtractProgress {
+                current_file: "complete".to_string(),
+                files_done: files.len(),
+                total_files: Some(files.len()),
+                bytes_written: bytes,
+            });
+        }
+
+        Ok(files)
+    })
+    .await
+    .map_err(|e| CogniaError::Installation(format!("Task join error: {}", e)))?
+}
+
+// ── Tests ──
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn tar_gz(
+        path: &Path,
+        build: impl FnOnce(&mut tar::Builder<flate2::write::GzEncoder<std::fs::File>>),
+    ) {
+        let file = std::fs::File::create(path).unwrap();
+        let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        build(&mut builder);
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    fn file_header(len: usize) -> tar::Header {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(len as u64);
+        header.set_mode(0o644);
+        header
+    }
+
+    /// Write a raw name into the header, bypassing the `..` check that
+    /// `Builder::append_data` performs
+    fn raw_file_header(name: &str, len: usize) -> tar::Header {
+        let mut header = file_header(len);
+        let gnu = header.as_gnu_mut().unwrap();
+        gnu.name[..name.len()].copy_from_slice(name.as_bytes());
+        header.set_cksum();
+        header
+    }
+
+    fn symlink_header() -> tar::Header {
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        header.set_mode(0o777);
+        header
+    }
+
+    #[test]
+    fn test_validate_extract_path_normal() {
+        let dest = Path::new("/tmp/extract");
+        let entry = Path::new("subdir/file.txt");
+        let result = validate_extract_path(dest, entry);
+        assert_eq!(result.unwrap(), dest.join("subdir").join("file.txt"));
+    }
+
+    #[test]
+    fn test_validate_extract_path_zipslip() {
+        let dest = Path::new("/tmp/extract");
+        let entry = Path::new("../../../etc/passwd");
+        let result = validate_extract_path(dest, entry);
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Zip-slip detected"));
+    }
+
+    #[test]
+    fn test_validate_extract_path_dotdot_in_middle() {
+        let dest = Path::new("/tmp/extract");
+        let entry = Path::new("foo/../../bar");
+        let result = validate_extract_path(dest, entry);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_extract_path_absolute() {
+        let dest = Path::new("/tmp/extract");
+        assert!(validate_extract_path(dest, Path::new("/etc/passwd")).is_err());
+    }
+
+    #[test]
+    fn test_normalize_lexically() {
+        assert_eq!(
+            normalize_lexically(Path::new("/a/./b/../c")),
+            PathBuf::from("/a/c")
+        );
+        assert_eq!(
+            normalize_lexically(Path::new("a/../../b")),
+            PathBuf::from("../b")
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_long_path_prefix() {
+        assert_eq!(
+            long_path(Path::new(r"C:\tools\node")),
+            PathBuf::from(r"\\?\C:\tools\node")
+        );
+        assert_eq!(
+            long_path(Path::new(r"\\server\share\x")),
+            PathBuf::from(r"\\?\UNC\server\share\x")
+        );
+        assert_eq!(
+            long_path(Path::new(r"\\?\C:\already")),
+            PathBuf::from(r"\\?\C:\already")
+        );
+    }
+
+    #[test]
+    fn test_symlink_mode_parse() {
+        assert_eq!("Copy".parse::<SymlinkMode>().unwrap(), SymlinkMode::Copy);
+        assert_eq!(
+            " junction ".parse::<SymlinkMode>().unwrap(),
+            SymlinkMode::Junction
+        );
+        assert!("hardlink".parse::<SymlinkMode>().is_err());
+        assert_eq!(SymlinkMode::default().to_string(), "junction");
+    }
+
+    #[tokio::test]
+    async fn test_extract_zip_native() {
+        let tmp = tempfile::tempdir().unwrap();
+        let zip_path = tmp.path().join("test.zip");
+        let extract_dir = tmp.path().join("out");
+
+        // Create a test zip in-memory
+        {
+            let file = std::fs::File::create(&zip_path).unwrap();
+            let mut writer = zip::ZipWriter::new(file);
+            let options: zip::write::FileOptions<'_, ()> = zip::write::FileOptions::default()
+                .compression_method(zip::CompressionMethod::Deflated);
+            writer.start_file("hello.txt", options).unwrap();
+            writer.write_all(b"Hello, world!").unwrap();
+            writer.start_file("subdir/nested.txt", options).unwrap();
+            writer.write_all(b"Nested content").unwrap();
+            writer.finish().unwrap();
+        }
+
+        let files = extract_archive(&zip_path, &extract_dir).await.unwrap();
+        assert_eq!(files.len(), 2);
+        assert!(extract_dir.join("hello.txt").exists());
+        assert!(extract_dir.join("subdir/nested.txt").exists());
+
+        let content = std::fs::read_to_string(extract_dir.join("hello.txt")).unwrap();
+        assert_eq!(content, "Hello, world!");
+    }
+
+    #[tokio::test]
+    async fn test_extract_zip_rejects_parent_dir_entry() {
+        let tmp = tempfile::tempdir().unwrap();
+        let zip_path = tmp.path().join("evil.zip");
+        let extract_dir = tmp.path().join("nested").join("out");
+
+        {
+            let file = std::fs::File::create(&zip_path).unwrap();
+            let mut writer = zip::ZipWriter::new(file);
+            let options: zip::write::FileOptions<'_, ()> = zip::write::FileOptions::default();
+            writer.start_file("ok.txt", options).unwrap();
+            writer.write_all(b"fine").unwrap();
+            writer.start_file("../../evil.txt", options).unwrap();
+            writer.write_all(b"pwned").unwrap();
+            writer.finish().unwrap();
+        }
+
+        let err = extract_archive(&zip_path, &extract_dir).await.unwrap_err();
+        assert!(err.to_string().contains("Zip-slip detected"));
+        assert!(!tmp.path().join("evil.txt").exists());
+    }
+
+    fn seven_zip(path: &Path, entries: &[(&str, &[u8])]) {
+        let mut writer = sevenz_rust2::ArchiveWriter::create(path).unwrap();
+        for (name, data) in entries {
+            writer
+                .push_archive_entry(sevenz_rust2::ArchiveEntry::new_file(name), Some(*data))
+                .unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_extract_7z_rejects_parent_dir_entry() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive = tmp.path().join("evil.7z");
+        let extract_dir = tmp.path().join("nested").join("out");
+        seven_zip(
+            &archive,
+            &[("ok.txt", b"fine"), ("../../evil.txt", b"pwned")],
+        );
+
+        let err = extract_archive(&archive, &extract_dir).await.unwrap_err();
+        assert!(err.to_string().contains("Zip-slip detected"));
+        assert!(!tmp.path().join("evil.txt").exists());
+        assert!(!extract_dir.join("ok.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_extract_7z_reports_only_extracted_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive = tmp.path().join("tool.7z");
+        let extract_dir = tmp.path().join("out");
+        std::fs::create_dir_all(&extract_dir).unwrap();
+        std::fs::write(extract_dir.join("existing.txt"), b"keep").unwrap();
+        seven_zip(&archive, &[("bin/tool", b"#!/bin/sh\n")]);
+
+        let files = extract_archive(&archive, &extract_dir).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].ends_with(Path::new("bin").join("tool")));
+        assert_eq!(
+            std::fs::read(extract_dir.join("bin").join("tool")).unwrap(),
+            b"#!/bin/sh\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extract_tar_gz_native() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive_path = tmp.path().join("test.tar.gz");
+        let extract_dir = tmp.path().join("out");
+
+        tar_gz(&archive_path, |tar| {
+            let data = b"Hello from tar.gz!";
+            let mut header = file_header(data.len());
+            tar.append_data(&mut header, "greeting.txt", &data[..])
+                .unwrap();
+        });
+
+        let files = extract_archive(&archive_path, &extract_dir).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(extract_dir.join("greeting.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_extract_tar_rejects_parent_dir_entry() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive_path = tmp.path().join("evil.tar.gz");
+        let extract_dir = tmp.path().join("nested").join("out");
+
+        tar_gz(&archive_path, |tar| {
+            let data = b"pwned";
+            let header = raw_file_header("../../evil.txt", data.len());
+            tar.append(&header, &data[..]).unwrap();
+        });
+
+        let err = extract_archive(&archive_path, &extract_dir)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Zip-slip detected"));
+        assert!(!tmp.path().join("evil.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_extract_tar_skips_escaping_symlink() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive_path = tmp.path().join("links.tar.gz");
+        let extract_dir = tmp.path().join("out");
+
+        tar_gz(&archive_path, |tar| {
+            let data = b"real";
+            let mut header = file_header(data.len());
+            tar.append_data(&mut header, "bin/real", &data[..]).unwrap();
+            tar.append_link(&mut symlink_header(), "bin/alias", "real")
+                .unwrap();
+            tar.append_link(&mut symlink_header(), "bin/escape", "../../secret")
+                .unwrap();
+            tar.append_link(&mut symlink_header(), "bin/absolute", "/etc/passwd")
+                .unwrap();
+        });
+
+        let options = ExtractOptions {
+            symlink_mode: SymlinkMode::Native,
+            on_progress: None,
+        };
+        let files = extract_archive_with_options(&archive_path, &extract_dir, options)
+            .await
+            .unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(
+            std::fs::read_to_string(extract_dir.join("bin/alias")).unwrap(),
+            "real"
+        );
+        assert!(std::fs::symlink_metadata(extract_dir.join("bin/escape")).is_err());
+        assert!(std::fs::symlink_metadata(extract_dir.join("bin/absolute")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_extract_tar_copy_mode_replaces_symlinks() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive_path = tmp.path().join("links.tar.gz");
+        let extract_dir = tmp.path().join("out");
+
+        tar_gz(&archive_path, |tar| {
+            // Link listed before its target, which is itself a link
+            tar.append_link(&mut symlink_header(), "current", "latest")
+                .unwrap();
+            tar.append_link(&mut symlink_header(), "latest", "v1")
+                .unwrap();
+            let data = b"v1 lib";
+            let mut header = file_header(data.len());
+            tar.append_data(&mut header, "v1/lib.txt", &data[..])
+                .unwrap();
+        });
+
+        let options = ExtractOptions {
+            symlink_mode: SymlinkMode::Copy,
+            on_progress: None,
+        };
+        extract_archive_with_options(&archive_path, &extract_dir, options)
+            .await
+            .unwrap();
+
+        for dir in ["latest", "current"] {
+            let path = extract_dir.join(dir);
+            assert!(!std::fs::symlink_metadata(&path)
+                .unwrap()
+                .file_type()
+                .is_symlink());
+            assert_eq!(
+                std::fs::read_to_string(path.join("lib.txt")).unwrap(),
+                "v1 lib"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_extract_unsupported_format() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive_path = tmp.path().join("test.rar");
+        std::fs::write(&archive_path, b"fake").unwrap();
+
+        let result = extract_archive(&archive_path, &tmp.path().join("out")).await;
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Unsupported archive format"));
+    }
+
+    #[tokio::test]
+    async fn test_extract_zip_with_progress() {
+        let tmp = tempfile::tempdir().unwrap();
+        let zip_path = tmp.path().join("progress.zip");
+        let extract_dir = tmp.path().join("out");
+
+        {
+            let file = std::fs::File::create(&zip_path).unwrap();
+            let mut writer = zip::ZipWriter::new(file);
+            let options: zip::write::FileOptions<'_, ()> = zip::write::FileOptions::default()
+                .compression_method(zip::CompressionMethod::Deflated);
+            writer.start_file("a.txt", options).unwrap();
+            writer.write_all(b"aaa").unwrap();
+            writer.start_file("b.txt", options).unwrap();
+            writer.write_all(b"bbbb").unwrap();
+            writer.finish().unwrap();
+        }
+
+        let progress_log: Arc<Mutex<Vec<(usize, Option<usize>, u64)>>> =
+            Arc::new(Mutex::new(Vec::new()));
+        let log_clone = progress_log.clone();
+
+        let callback = move |p: ExtractProgress| {
+            log_clone
+                .lock()
+                .unwrap()
+                .push((p.files_done, p.total_files, p.bytes_written));
+        };
+
+        let files =
+            extract_archive_with_progress(&zip_path, &extract_dir, Some(Arc::new(callback)))
+                .await
+                .unwrap();
+        assert_eq!(files.len(), 2);
+
+        let log = progress_log.lock().unwrap();
+        assert_eq!(log.len(), 2);
+        assert_eq!(log[0], (1, Some(2), 3));
+        assert_eq!(log[1], (2, Some(2), 7));
+    }
+
+    #[test]
+    fn test_throttle_progress_keeps_final_event() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let cb = throttle_progress(Duration::from_secs(3600), move |p| {
+            seen_clone.lock().unwrap().push(p.files_done)
+        });
+        for files_done in 1..=3 {
+            cb(ExtractProgress {
+                current_file: String::new(),
+                files_done,
+                total_files: Some(3),
+                bytes_written: 0,
+            });
+        }
+        assert_eq!(*seen.lock().unwrap(), vec![1, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_extract_tar_bz2_native() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive_path = tmp.path().join("test.tar.bz2");
+        let extract_dir = tmp.path().join("out");
+
+        {
+            let file = std::fs::File::create(&archive_path).unwrap();
+            let encoder = bzip2::write::BzEncoder::new(file, bzip2::Compression::default());
+            let mut tar_builder = tar::Builder::new(encoder);
+
+            let data = b"Hello from tar.bz2!";
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            tar_builder
+                .append_data(&mut header, "bz2-file.txt", &data[..])
+                .unwrap();
+            tar_builder.finish().unwrap();
+        }
+
+        let files = extract_archive(&archive_path, &extract_dir).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(extract_dir.join("bz2-file.txt").exists());
+
+        let content = std::fs::read_to_string(extract_dir.join("bz2-file.txt")).unwrap();
+        assert_eq!(content, "Hello from tar.bz2!");
+    }
+
+    #[tokio::test]
+    async fn test_extract_tar_xz_native() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive_path = tmp.path().join("test.tar.xz");
+        let extract_dir = tmp.path().join("out");
+
+        {
+            let file = std::fs::File::create(&archive_path).unwrap();
+            let encoder = xz2::write::XzEncoder::new(file, 6);
+            let mut tar_builder = tar::Builder::new(encoder);
+
+            let data = b"Hello from tar.xz!";
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            tar_builder
+                .append_data(&mut header, "xz-file.txt", &data[..])
+                .unwrap();
+            tar_builder.finish().unwrap();
+        }
+
+        let files = extract_archive(&archive_path, &extract_dir).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(extract_dir.join("xz-file.txt").exists());
+
+        let content = std::fs::read_to_string(extract_dir.join("xz-file.txt")).unwrap();
+        assert_eq!(content, "Hello from tar.xz!");
+    }
+
+    #[tokio::test]
+    async fn test_extract_tar_zst_native() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive_path = tmp.path().join("test.tar.zst");
+        let extract_dir = tmp.path().join("out");
+
+        {
+            let file = std::fs::File::create(&archive_path).unwrap();
+            let encoder = zstd::Encoder::new(file, 3).unwrap().auto_finish();
+            let mut tar_builder = tar::Builder::new(encoder);
+
+            let data = b"Hello from tar.zst!";
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            tar_builder
+                .append_data(&mut header, "zst-file.txt", &data[..])
+                .unwrap();
+            tar_builder.finish().unwrap();
+        }
+
+        let files = extract_archive(&archive_path, &extract_dir).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(extract_dir.join("zst-file.txt").exists());
+
+        let content = std::fs::read_to_string(extract_dir.join("zst-file.txt")).unwrap();
+        assert_eq!(content, "Hello from tar.zst!");
+    }
+
+    #[tokio::test]
+    async fn test_extract_tar_gz_multiple_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive_path = tmp.path().join("multi.tar.gz");
+        let extract_dir = tmp.path().join("out");
+
+        tar_gz(&archive_path, |tar| {
+            for i in 0..5 {
+                let data = format!("File content {}", i);
+                let mut header = file_header(data.len());
+                tar.append_data(&mut header, format!("dir/file{}.txt", i), data.as_bytes())
+                    .unwrap();
+            }
+        });
+
+        // No directory entry: parents are created on demand
+        let files = extract_archive(&archive_path, &extract_dir).await.unwrap();
+        assert_eq!(files.len(), 5);
+        for i in 0..5 {
+            let path = extract_dir.join(format!("dir/file{}.txt", i));
+            assert!(path.exists(), "Missing file: {}", path.display());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_extract_standalone_xz_rejected() {
+        let tmp = tempfile::tempdir().unwrap();
+        // A plain .xz (not .tar.xz) should be rejected
+        let archive_path = tmp.path().join("test.xz");
+        std::fs::write(&archive_path, b"fake xz").unwrap();
+
+        let result = extract_archive(&archive_path, &tmp.path().join("out")).await;
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Standalone .xz not supported"));
+    }
+
+    #[test]
+    fn test_extract_progress_serialize() {
+        let progress = ExtractProgress {
+            current_file: "test.txt".to_string(),
+            files_done: 1,
+            total_files: Some(5),
+            bytes_written: 42,
+        };
+        let json = serde_json::to_string(&progress).unwrap();
+        assert!(json.contains("test.txt"));
+        assert!(json.contains("\"files_done\":1"));
+        assert!(json.contains("\"total_files\":5"));
+        assert!(json.contains("\"bytes_written\":42"));
+    }
+}
@@ -236,7 +236,7 @@ pub struct BackupCleanupResult {
     pub error: Option<String>,
 }
 
-fn issue(
+pub(crate) fn issue(
     code: &str,
     message: impl Into<String>,
     content_type: Option<String>,
@@ -294,7 +294,7 @@ fn empty_manifest(note: Option<&str>) -> BackupManifest {
     }
 }
 
-fn operation_failure_reason(operation: &str, err: &CogniaError) -> String {
+pub(crate) fn operation_failure_reason(operation: &str, err: &CogniaError) -> String {
     let fallback = format!("backup_{}_failed", operation);
     match err {
         CogniaError::PermissionDenied(_) => format!("backup_{}_permission_denied", operation),
@@ -2,8 +2,11 @@
 use crate::core::HistoryManager;
 use crate::error::{CogniaError, CogniaResult};
 use crate::platform::disk;
-use crate::provider::{InstallRequest, InstalledFilter, ProviderRegistry, UninstallRequest};
+use crate::provider::{
+    InstallRequest, InstalledFilter, PackageVerification, ProviderRegistry, UninstallRequest,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -36,6 +39,12 @@ pub struct BatchResult {
     pub successful: Vec<BatchItemResult>,
     pub failed: Vec<BatchItemError>,
     pub skipped: Vec<BatchItemSkipped>,
+    /// Updates that failed verification and were restored to the previous version
+    #[serde(default)]
+    pub rolled_back: Vec<BatchItemRollback>,
+    /// Packages left alone because they are pinned
+    #[serde(default)]
+    pub pinned: Vec<BatchItemSkipped>,
     pub total_time_ms: u64,
 }
 
@@ -61,6 +70,25 @@ pub struct BatchItemSkipped {
     pub reason: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchItemRollback {
+    pub name: String,
+    pub provider: String,
+    pub from_version: String,
+    pub to_version: String,
+    pub reason: String,
+    /// Whether the package was pinned to `to_version` to prevent re-update
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+/// Options for [`BatchManager::batch_update_with_options`]
+#[derive(Debug, Clone, Default)]
+pub struct BatchUpdateOptions {
+    /// Verify each package after updating it and roll back the ones that fail
+    pub verify_after_update: bool,
+}
+
 /// Progress events for batch operations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -90,6 +118,17 @@ pub enum BatchProgress {
         current: usize,
         total: usize,
     },
+    Verifying {
+        package: String,
+        current: usize,
+        total: usize,
+    },
+    RollingBack {
+        package: String,
+        to_version: String,
+        current: usize,
+        total: usize,
+    },
     Completed {
         result: BatchResult,
     },
@@ -123,6 +162,11 @@ pub fn cancel(&self) {
     pub fn reset(&self) {
         self.cancelled.store(false, Ordering::SeqCst);
     }
+
+    /// Shared flag, for registering the batch as a cancellable job
+    pub fn flag(&self) -> Arc<AtomicBool> {
+        self.cancelled.clone()
+    }
 }
 
 /// Batch operations manager
@@ -233,6 +277,8 @@ pub async fn batch_install<F>(
                 successful,
                 failed,
                 skipped,
+                rolled_back: Vec::new(),
+                pinned: Vec::new(),
                 total_time_ms: start_time.elapsed().as_millis() as u64,
             });
         }
@@ -270,6 +316,9 @@ pub async fn batch_install<F>(
                     }
                 }
             }
+            if self.should_cancel() {
+                return Err(CogniaError::Cancelled);
+            }
         } else {
             for (idx, spec) in to_install.iter().enumerate() {
                 // Check for cancellation before each package
@@ -331,6 +380,8 @@ pub async fn batch_install<F>(
             successful,
             failed,
             skipped,
+            rolled_back: Vec::new(),
+            pinned: Vec::new(),
             total_time_ms: start_time.elapsed().as_millis() as u64,
         };
 
@@ -411,6 +462,8 @@ pub async fn batch_uninstall<F>(
             successful,
             failed,
             skipped,
+            rolled_back: Vec::new(),
+            pinned: Vec::new(),
             total_time_ms: start_time.elapsed().as_millis() as u64,
         };
 
@@ -425,6 +478,21 @@ pub async fn batch_uninstall<F>(
     pub async fn batch_update<F>(
         &self,
         packages: Option<Vec<String>>,
+        on_progress: F,
+    ) -> CogniaResult<BatchResult>
+    where
+        F: FnMut(BatchProgress) + Send,
+    {
+        self.batch_update_with_options(packages, BatchUpdateOptions::default(), on_progress)
+            .await
+    }
+
+    /// Update multiple packages, optionally verifying each update and rolling
+    /// back the packages that fail verification
+    pub async fn batch_update_with_options<F>(
+        &self,
+        packages: Option<Vec<String>>,
+        options: BatchUpdateOptions,
         mut on_progress: F,
     ) -> CogniaResult<BatchResult>
     where
@@ -469,8 +537,25 @@ pub async fn batch_update<F>(
         let mut successful = Vec::new();
         let mut failed = Vec::new();
         let mut skipped = Vec::new();
+        let mut rolled_back = Vec::new();
+        let mut pinned = Vec::new();
 
         for (idx, spec) in to_update.iter().enumerate() {
+            if let Some(pin) = find_pin(
+                &self.settings.provider_settings.pinned_packages,
+                &spec.name,
+                spec.provider.as_deref(),
+            ) {
+                pinned.push(BatchItemSkipped {
+                    name: spec.name.clone(),
+                    reason: match pin {
+                        Some(version) => format!("Pinned to {}", version),
+                        None => "Pinned".into(),
+                    },
+                });
+                continue;
+            }
+
             on_progress(BatchProgress::Resolving {
                 package: spec.name.clone(),
                 current: idx + 1,
@@ -498,25 +583,109 @@ pub async fn batch_update<F>(
                         provider: Some(provider.clone()),
                     };
 
+                    // Record the update before attempting it so the previous
+                    // version survives an interrupted update
+                    let history_id =
+                        HistoryManager::begin_update(&spec.name, &current, &latest, &provider)
+                            .await
+                            .ok();
+
                     match self.install_single(&spec, true, true).await {
                         Ok(mut item) => {
                             item.action = format!("updated {} -> {}", current, latest);
-                            let _ = HistoryManager::record_update(
-                                &spec.name, &current, &latest, &provider, true, None,
-                            )
-                            .await;
-                            successful.push(item);
+                            match history_id {
+                                Some(ref id) => {
+                                    let _ = HistoryManager::complete_entry(id, true, None).await;
+                                }
+                                None => {
+                                    let _ = HistoryManager::record_update(
+                                        &spec.name, &current, &latest, &provider, true, None,
+                                    )
+                                    .await;
+                                }
+                            }
+
+                            if !options.verify_after_update {
+                                successful.push(item);
+                                continue;
+                            }
+
+                            on_progress(BatchProgress::Verifying {
+                                package: spec.name.clone(),
+                                current: idx + 1,
+                                total,
+                            });
+                            let verification =
+                                self.verify_single(&spec.name, &latest, &provider).await;
+                            if verification.passed {
+                                successful.push(item);
+                                continue;
+                            }
+
+                            let reason = verification.message.unwrap_or_else(|| {
+                                format!("{} verification failed", verification.method)
+                            });
+                            let to_version =
+                                HistoryManager::get_version_before_update(&spec.name, &provider)
+                                    .await
+                                    .ok()
+                                    .flatten()
+                                    .unwrap_or_else(|| current.clone());
+
+                            on_progress(BatchProgress::RollingBack {
+                                package: spec.name.clone(),
+                                to_version: to_version.clone(),
+                                current: idx + 1,
+                                total,
+                            });
+                            match self
+                                .rollback_single(&spec.name, &to_version, &provider)
+                                .await
+                            {
+                                Ok(()) => rolled_back.push(BatchItemRollback {
+                                    name: spec.name.clone(),
+                                    provider: provider.clone(),
+                                    from_version: latest.clone(),
+                                    to_version,
+                                    reason,
+                                    pinned: false,
+                                }),
+                                Err(e) => failed.push(BatchItemError {
+                                    name: spec.name.clone(),
+                                    error: format!(
+                                        "Verification failed ({}) and rollback to {} failed: {}",
+                                        reason, to_version, e
+                                    ),
+                                    recoverable: false,
+                                    suggestion: Some(format!(
+                                        "Reinstall {} {} manually",
+                                        spec.name, to_version
+                                    )),
+                                }),
+                            }
                         }
                         Err(item) => {
-                            let _ = HistoryManager::record_update(
-                                &spec.name,
-                                &current,
-                                &latest,
-                                &provider,
-                                false,
-                                Some(item.error.clone()),
-                            )
-                            .await;
+                            match history_id {
+                                Some(ref id) => {
+                                    let _ = HistoryManager::complete_entry(
+                                        id,
+                                        false,
+                                        Some(item.error.clone()),
+                                    )
+                                    .await;
+                                }
+                                None => {
+                                    let _ = HistoryManager::record_update(
+                                        &spec.name,
+                                        &current,
+                                        &latest,
+                                        &provider,
+                                        false,
+                                        Some(item.error.clone()),
+                                    )
+                                    .await;
+                                }
+                            }
                             failed.push(item);
                         }
                     }
@@ -542,6 +711,8 @@ pub async fn batch_update<F>(
             successful,
             failed,
             skipped,
+            rolled_back,
+            pinned,
             total_time_ms: start_time.elapsed().as_millis() as u64,
         };
 
@@ -552,6 +723,65 @@ pub async fn batch_update<F>(
         Ok(result)
     }
 
+    /// Run the provider's post-update verification; a provider that can no
+    /// longer be found counts as a failure
+    async fn verify_single(
+        &self,
+        name: &str,
+        version: &str,
+        provider_id: &str,
+    ) -> PackageVerification {
+        let provider = self.registry.read().await.get(provider_id);
+        let Some(provider) = provider else {
+            return PackageVerification::failed(
+                "provider",
+                format!("Provider not found: {}", provider_id),
+            );
+        };
+
+        match provider.verify_installed(name, version).await {
+            Ok(verification) => verification,
+            Err(e) => PackageVerification::failed("provider", e.to_string()),
+        }
+    }
+
+    /// Reinstall a single package at `to_version`, recording the rollback in history
+    async fn rollback_single(
+        &self,
+        name: &str,
+        to_version: &str,
+        provider_id: &str,
+    ) -> Result<(), String> {
+        let provider = self
+            .registry
+            .read()
+            .await
+            .get(provider_id)
+            .ok_or_else(|| format!("Provider not found: {}", provider_id))?;
+
+        let result = provider
+            .install(InstallRequest {
+                name: name.to_string(),
+                version: Some(to_version.to_string()),
+                global: true,
+                force: true,
+            })
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string());
+
+        let _ = HistoryManager::record_rollback(
+            name,
+            to_version,
+            provider_id,
+            result.is_ok(),
+            result.as_ref().err().cloned(),
+        )
+        .await;
+
+        result
+    }
+
     async fn install_parallel<F>(
         &self,
         specs: Vec<PackageSpec>,
@@ -567,14 +797,20 @@ async fn install_parallel<F>(
         let total = specs.len();
         let registry = self.registry.clone();
         let max_retries = self.max_retries;
+        let cancel_token = self.cancel_token.clone();
 
         let results: Vec<_> = stream::iter(specs.into_iter().enumerate())
             .map(|(idx, spec)| {
                 let registry = registry.clone();
+                let cancel_token = cancel_token.clone();
                 async move {
+                    // Packages not yet started when the batch is cancelled are left out
+                    if cancel_token.is_some_and(|token| token.is_cancelled()) {
+                        return None;
+                    }
                     let result =
                         Self::install_with_retry(registry, &spec, global, force, max_retries).await;
-                    (idx, spec, result)
+                    Some((idx, spec, result))
                 }
             })
             .buffer_unordered(self.max_parallel)
@@ -582,7 +818,7 @@ async fn install_parallel<F>(
             .await;
 
         // Sort by original index and convert to results
-        let mut sorted_results: Vec<_> = results.into_iter().collect();
+        let mut sorted_results: Vec<_> = results.into_iter().flatten().collect();
         sorted_results.sort_by_key(|(idx, _, _)| *idx);
 
         sorted_results
@@ -838,6 +1074,27 @@ fn get_error_suggestion(error: &str) -> Option<String> {
     }
 }
 
+/// Look up a package in the pinned packages map, whose keys are either
+/// `provider:name` or a bare `name` that applies to every provider
+fn find_pin<'a>(
+    pins: &'a HashMap<String, Option<String>>,
+    name: &str,
+    provider: Option<&str>,
+) -> Option<&'a Option<String>> {
+    pins.iter()
+        .find(|(key, _)| match key.split_once(':') {
+            Some((pin_provider, pin_name)) => {
+                pin_name.eq_ignore_ascii_case(name)
+                    && match provider {
+                        Some(p) => pin_provider.eq_ignore_ascii_case(p),
+                        None => true,
+                    }
+            }
+            None => key.eq_ignore_ascii_case(name),
+        })
+        .map(|(_, version)| version)
+}
+
 /// Package specification parser
 #[derive(Debug, Clone)]
 pub struct PackageSpec {
@@ -1107,6 +1364,8 @@ fn test_batch_result_empty() {
             successful: vec![],
             failed: vec![],
             skipped: vec![],
+            rolled_back: vec![],
+            pinned: vec![],
             total_time_ms: 0,
         };
         assert!(result.successful.is_empty());
@@ -1405,6 +1664,8 @@ fn test_batch_result_serde_roundtrip() {
                 name: "existing".into(),
                 reason: "Already installed".into(),
             }],
+            rolled_back: vec![],
+            pinned: vec![],
             total_time_ms: 1234,
         };
 
@@ -1416,6 +1677,46 @@ fn test_batch_result_serde_roundtrip() {
         assert_eq!(deser.total_time_ms, 1234);
     }
 
+    #[test]
+    fn test_batch_result_deserializes_without_update_report_fields() {
+        let json = r#"{"successful":[],"failed":[],"skipped":[],"total_time_ms":5}"#;
+        let result: BatchResult = serde_json::from_str(json).unwrap();
+        assert!(result.rolled_back.is_empty());
+        assert!(result.pinned.is_empty());
+    }
+
+    #[test]
+    fn test_batch_progress_rolling_back_serde() {
+        let progress = BatchProgress::RollingBack {
+            package: "ripgrep".into(),
+            to_version: "14.0.0".into(),
+            current: 1,
+            total: 2,
+        };
+        let json = serde_json::to_string(&progress).unwrap();
+        assert!(json.contains("\"type\":\"rolling_back\""));
+        assert!(json.contains("\"to_version\":\"14.0.0\""));
+    }
+
+    #[test]
+    fn test_find_pin() {
+        let mut pins = HashMap::new();
+        pins.insert("npm:lodash".to_string(), Some("4.17.20".to_string()));
+        pins.insert("ripgrep".to_string(), None);
+
+        assert_eq!(
+            find_pin(&pins, "lodash", Some("npm")),
+            Some(&Some("4.17.20".to_string()))
+        );
+        assert_eq!(
+            find_pin(&pins, "lodash", None),
+            Some(&Some("4.17.20".to_string()))
+        );
+        assert!(find_pin(&pins, "lodash", Some("pnpm")).is_none());
+        assert_eq!(find_pin(&pins, "ripgrep", Some("cargo")), Some(&None));
+        assert!(find_pin(&pins, "serde", Some("cargo")).is_none());
+    }
+
     #[test]
     fn test_looks_like_version_dist_tag_prefix() {
         assert!(PackageSpec::looks_like_version("beta-1"));
@@ -0,0 +1,744 @@
+//! `environment.yml` and `conda-lock.yml` parsing, and the package diff
+//! between an environment spec and an existing conda environment.
+
+use crate::error::{CogniaError, CogniaResult};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+/// Channel conda reports for packages installed by pip
+pub const PYPI_CHANNEL: &str = "pypi";
+
+/// Channels the `defaults` alias stands for
+const DEFAULTS_CHANNELS: &[&str] = &["pkgs/main", "pkgs/r", "pkgs/msys2", "pkgs/free"];
+/// Characters that start or join version constraints in a match spec
+const VERSION_OPERATORS: &[char] = &['<', '>', '=', '!', '~', ',', '|'];
+
+/// Parsed `environment.yml`
+#[derive(Debug, Clone, Default)]
+pub struct CondaEnvSpec {
+    pub name: Option<String>,
+    /// Channels in priority order, without `nodefaults`
+    pub channels: Vec<String>,
+    pub conda: Vec<CondaMatchSpec>,
+    pub pip: Vec<PipRequirement>,
+    /// Entries that cannot be compared (`-r file`, `-e .`, bare URLs)
+    pub unsupported: Vec<String>,
+}
+
+/// One conda dependency: `[channel::]name[ version[ build]]` or `name=version=build`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CondaMatchSpec {
+    pub raw: String,
+    /// Lowercase package name
+    pub name: String,
+    pub channel: Option<String>,
+    /// Version constraint in conda syntax (`=1.2` is fuzzy, `1.2` exact)
+    pub version: Option<String>,
+    pub build: Option<String>,
+}
+
+/// One entry of the `pip:` section
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipRequirement {
+    pub raw: String,
+    /// Normalized per PEP 503 (lowercase, `-` separators)
+    pub name: String,
+    pub version: Option<String>,
+}
+
+/// A package as reported by `conda list --json`
+#[derive(Debug, Clone, Deserialize)]
+pub struct InstalledCondaPackage {
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub channel: String,
+    #[serde(default)]
+    pub build_string: String,
+}
+
+impl InstalledCondaPackage {
+    pub fn is_pip(&self) -> bool {
+        self.channel == PYPI_CHANNEL
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CondaChangeReason {
+    /// In the spec, not installed
+    Missing,
+    /// Installed version or build does not satisfy the spec
+    VersionMismatch,
+    /// Installed from a channel the spec does not allow
+    ChannelMismatch,
+    /// Installed, not in the spec and not needed by anything that is
+    NotInSpec,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CondaPackageChange {
+    pub name: String,
+    pub installed_version: Option<String>,
+    pub installed_channel: Option<String>,
+    /// Spec passed to the installer, `None` for removals
+    pub spec: Option<String>,
+    pub reason: CondaChangeReason,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CondaPackageDiff {
+    pub additions: Vec<CondaPackageChange>,
+    pub removals: Vec<CondaPackageChange>,
+    pub changes: Vec<CondaPackageChange>,
+}
+
+impl CondaPackageDiff {
+    pub fn is_empty(&self) -> bool {
+        self.additions.is_empty() && self.removals.is_empty() && self.changes.is_empty()
+    }
+
+    /// Specs to install for the additions and changes
+    pub fn install_specs(&self) -> Vec<String> {
+        self.additions
+            .iter()
+            .chain(&self.changes)
+            .filter_map(|change| change.spec.clone())
+            .collect()
+    }
+
+    pub fn removal_names(&self) -> Vec<String> {
+        self.removals.iter().map(|c| c.name.clone()).collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RawEnvironmentYml {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    channels: Vec<String>,
+    #[serde(default)]
+    dependencies: Vec<serde_yaml::Value>,
+}
+
+fn take_name(spec: &str) -> (String, &str) {
+    let end = spec
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.')))
+        .unwrap_or(spec.len());
+    (spec[..end].to_string(), &spec[end..])
+}
+
+/// Parse a conda match spec. Bracketed options (`numpy[version='>=1']`)
+/// are not supported and yield `None`.
+pub fn parse_match_spec(raw: &str) -> Option<CondaMatchSpec> {
+    let spec = raw.trim();
+    if spec.is_empty() || spec.contains('[') {
+        return None;
+    }
+    let (channel, spec) = match spec.rsplit_once("::") {
+        Some((channel, rest)) => (Some(channel.trim().to_string()), rest.trim()),
+        None => (None, spec),
+    };
+    let (name, rest) = take_name(spec);
+    if name.is_empty() {
+        return None;
+    }
+    let rest = rest.trim();
+    let fuzzy = rest.strip_prefix('=').filter(|r| !r.starts_with('='));
+    let (version, build) = if let Some(pinned) = fuzzy {
+        // name=1.2[=build]: the version part is fuzzy
+        let (version, build) = match pinned.split_once('=') {
+            Some((version, build)) => (version.trim(), Some(build.trim().to_string())),
+            None => (pinned.trim(), None),
+        };
+        (
+            (!version.is_empty()).then(|| format!("={}", version)),
+            build,
+        )
+    } else {
+        // `name 1.2 build`, but `python >=3.8, <3.12` is all version: the
+        // last token is only a build when it can't continue a constraint
+        let mut tokens: Vec<&str> = rest.split_whitespace().collect();
+        let has_build = matches!(
+            tokens.as_slice(),
+            [.., prev, last]
+                if !prev.ends_with(VERSION_OPERATORS) && !last.starts_with(VERSION_OPERATORS)
+        );
+        let build = if has_build { tokens.pop() } else { None };
+        (
+            (!tokens.is_empty()).then(|| tokens.concat()),
+            build.map(|b| b.to_string()),
+        )
+    };
+    Some(CondaMatchSpec {
+        raw: raw.trim().to_string(),
+        name: name.to_lowercase(),
+        channel,
+        version,
+        build: build.filter(|b| !b.is_empty()),
+    })
+}
+
+/// PEP 503 normalized project name
+pub fn normalize_pip_name(name: &str) -> String {
+    name.to_lowercase().replace(['_', '.'], "-")
+}
+
+/// Parse a pip requirement. Options, editable installs and nameless URLs
+/// yield `None`.
+pub fn parse_pip_requirement(raw: &str) -> Option<PipRequirement> {
+    let requirement = raw.trim();
+    if requirement.is_empty() || requirement.starts_with('-') {
+        return None;
+    }
+    let requirement = requirement
+        .split_once(';')
+        .map_or(requirement, |(req, _)| req)
+        .trim();
+    let (name, rest) = take_name(requirement);
+    if name.is_empty() || rest.trim_start().starts_with("://") {
+        return None;
+    }
+    let mut rest = rest.trim();
+    if rest.starts_with('[') {
+        rest = rest.split_once(']').map_or("", |(_, after)| after).trim();
+    }
+    // `name @ url` pins a source, not a version
+    let version = (!rest.is_empty() && !rest.starts_with('@')).then(|| rest.replace(' ', ""));
+    Some(PipRequirement {
+        raw: raw.trim().to_string(),
+        name: normalize_pip_name(&name),
+        version,
+    })
+}
+
+/// Parse the content of an `environment.yml`
+pub fn parse_environment_yml(content: &str) -> CogniaResult<CondaEnvSpec> {
+    let raw: RawEnvironmentYml = serde_yaml::from_str(content)
+        .map_err(|e| CogniaError::Parse(format!("Invalid environment.yml: {}", e)))?;
+    let mut spec = CondaEnvSpec {
+        name: raw.name,
+        channels: raw
+            .channels
+            .into_iter()
+            .filter(|c| c != "nodefaults")
+            .collect(),
+        ..Default::default()
+    };
+    for dependency in raw.dependencies {
+        match dependency {
+            serde_yaml::Value::String(entry) => match parse_match_spec(&entry) {
+                Some(parsed) => spec.conda.push(parsed),
+                None => spec.unsupported.push(entry),
+            },
+            serde_yaml::Value::Mapping(map) => {
+                let Some(serde_yaml::Value::Sequence(entries)) = map.get("pip") else {
+                    continue;
+                };
+                for entry in entries.iter().filter_map(|e| e.as_str()) {
+                    match parse_pip_requirement(entry) {
+                        Some(parsed) => spec.pip.push(parsed),
+                        None => spec.unsupported.push(format!("pip: {}", entry)),
+                    }
+                }
+            }
+            other => spec
+                .unsupported
+                .push(serde_yaml::to_string(&other).unwrap_or_default()),
+        }
+    }
+    Ok(spec)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum VersionToken {
+    Number(u64),
+    Text(String),
+}
+
+fn version_tokens(version: &str) -> Vec<VersionToken> {
+    let mut tokens = Vec::new();
+    for part in version.split(['.', '-', '_', '+']) {
+        let mut rest = part;
+        while !rest.is_empty() {
+            let numeric = rest.starts_with(|c: char| c.is_ascii_digit());
+            let end = rest
+                .find(|c: char| c.is_ascii_digit() != numeric)
+                .unwrap_or(rest.len());
+            let (token, tail) = rest.split_at(end);
+            tokens.push(if numeric {
+                VersionToken::Number(token.parse().unwrap_or(u64::MAX))
+            } else {
+                VersionToken::Text(token.to_lowercase())
+            });
+            rest = tail;
+        }
+    }
+    tokens
+}
+
+/// Order two versions component by component. Trailing zeros are ignored
+/// and a trailing text component (`1.0rc1`) sorts before the release.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (a, b) = (version_tokens(a), version_tokens(b));
+    for i in 0..a.len().max(b.len()) {
+        let ordering = match (a.get(i), b.get(i)) {
+            (Some(VersionToken::Number(x)), Some(VersionToken::Number(y))) => x.cmp(y),
+            (Some(VersionToken::Text(x)), Some(VersionToken::Text(y))) => x.cmp(y),
+            (Some(VersionToken::Number(_)), Some(VersionToken::Text(_))) => Ordering::Greater,
+            (Some(VersionToken::Text(_)), Some(VersionToken::Number(_))) => Ordering::Less,
+            (Some(VersionToken::Number(0)), None) | (None, Some(VersionToken::Number(0))) => {
+                Ordering::Equal
+            }
+            (Some(VersionToken::Number(_)), None) => Ordering::Greater,
+            (None, Some(VersionToken::Number(_))) => Ordering::Less,
+            (Some(VersionToken::Text(_)), None) => Ordering::Less,
+            (None, Some(VersionToken::Text(_))) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+/// `1.2.*` / `1.2*` style prefix match; `1.2.*` also matches `1.2`
+fn glob_matches(pattern: &str, version: &str) -> bool {
+    let prefix = pattern.trim_end_matches('*');
+    version.starts_with(prefix) || version == prefix.trim_end_matches('.')
+}
+
+/// Conda's `=1.2`: `1.2` itself or any `1.2.<more>`
+fn fuzzy_matches(target: &str, version: &str) -> bool {
+    let target = target.trim_end_matches('*').trim_end_matches('.');
+    version == target
+        || version
+            .strip_prefix(target)
+            .is_some_and(|rest| rest.starts_with('.'))
+}
+
+fn constraint_matches(constraint: &str, version: &str) -> bool {
+    let constraint = constraint.trim();
+    if constraint.is_empty() || constraint == "*" {
+        return true;
+    }
+    for op in ["===", "==", "!=", ">=", "<=", "~=", ">", "<", "="] {
+        let Some(target) = constraint.strip_prefix(op) else {
+            continue;
+        };
+        let target = target.trim();
+        let ordering = || compare_versions(version, target);
+        return match op {
+            "===" | "==" if target.contains('*') => glob_matches(target, version),
+            "===" | "==" => ordering() == Ordering::Equal,
+            "!=" if target.contains('*') => !glob_matches(target, version),
+            "!=" => ordering() != Ordering::Equal,
+            ">=" => ordering() != Ordering::Less,
+            "<=" => ordering() != Ordering::Greater,
+            ">" => ordering() == Ordering::Greater,
+            "<" => ordering() == Ordering::Less,
+            "~=" => {
+                let release = target.rsplit_once('.').map_or(target, |(head, _)| head);
+                ordering() != Ordering::Less && fuzzy_matches(release, version)
+            }
+            _ => fuzzy_matches(target, version),
+        };
+    }
+    if constraint.contains('*') {
+        glob_matches(constraint, version)
+    } else {
+        compare_versions(version, constraint) == Ordering::Equal
+    }
+}
+
+/// Whether `version` satisfies a conda or pip constraint (`,` = and, `|` = or)
+pub fn version_matches(constraint: &str, version: &str) -> bool {
+    constraint.split('|').any(|alternative| {
+        alternative
+            .split(',')
+            .all(|part| constraint_matches(part, version))
+    })
+}
+
+/// Channel name without the anaconda.org host or a platform subdir
+fn normalize_channel(channel: &str) -> String {
+    let channel = channel.trim().trim_end_matches('/');
+    let channel = ["conda.anaconda.org/", "repo.anaconda.com/"]
+        .iter()
+        .find_map(|host| channel.split_once(host).map(|(_, rest)| rest))
+        .unwrap_or(channel);
+    let channel = channel
+        .rsplit_once('/')
+        .filter(|(_, subdir)| *subdir == "noarch" || subdir.contains('-'))
+        .map_or(channel, |(head, _)| head);
+    channel.to_lowercase()
+}
+
+fn channel_allowed(installed: &str, allowed: &[String]) -> bool {
+    let installed = normalize_channel(installed);
+    allowed.iter().any(|channel| {
+        let channel = normalize_channel(channel);
+        channel == installed
+            || (channel == "defaults" && DEFAULTS_CHANNELS.contains(&installed.as_str()))
+    })
+}
+
+fn change(
+    name: &str,
+    installed: Option<&InstalledCondaPackage>,
+    spec: Option<String>,
+    reason: CondaChangeReason,
+) -> CondaPackageChange {
+    CondaPackageChange {
+        name: name.to_string(),
+        installed_version: installed.map(|p| p.version.clone()),
+        installed_channel: installed.map(|p| p.channel.clone()),
+        spec,
+        reason,
+    }
+}
+
+/// Conda packages to add, change or remove so `installed` matches `spec`.
+/// Only names in `removable` (installed, and required by nothing the spec
+/// keeps) are proposed for removal.
+pub fn diff_conda_packages(
+    spec: &CondaEnvSpec,
+    installed: &[InstalledCondaPackage],
+    removable: &HashSet<String>,
+) -> CondaPackageDiff {
+    let mut diff = CondaPackageDiff::default();
+    let mut seen = HashSet::new();
+    for wanted in &spec.conda {
+        if !seen.insert(wanted.name.clone()) {
+            continue;
+        }
+        let current = installed
+            .iter()
+            .find(|p| !p.is_pip() && p.name.to_lowercase() == wanted.name);
+        let Some(current) = current else {
+            diff.additions.push(change(
+                &wanted.name,
+                None,
+                Some(wanted.raw.clone()),
+                CondaChangeReason::Missing,
+            ));
+            continue;
+        };
+        let version_ok = wanted
+            .version
+            .as_deref()
+            .map(|v| version_matches(v, &current.version))
+            .unwrap_or(true);
+        let build_ok = wanted
+            .build
+            .as_deref()
+            .map(|b| glob_matches(b, &current.build_string))
+            .unwrap_or(true);
+        let allowed: Vec<String> = match &wanted.channel {
+            Some(channel) => vec![channel.clone()],
+            None => spec.channels.clone(),
+        };
+        if !(version_ok && build_ok) {
+            diff.changes.push(change(
+                &wanted.name,
+                Some(current),
+                Some(wanted.raw.clone()),
+                CondaChangeReason::VersionMismatch,
+            ));
+        } else if !allowed.is_empty() && !channel_allowed(&current.channel, &allowed) {
+            // Pin the channel so the reinstall does not keep the current one
+            let pinned = match &wanted.channel {
+                Some(_) => wanted.raw.clone(),
+                None => format!("{}::{}", allowed[0], wanted.raw),
+            };
+            diff.changes.push(change(
+                &wanted.name,
+                Some(current),
+                Some(pinned),
+                CondaChangeReason::ChannelMismatch,
+            ));
+        }
+    }
+    for package in installed.iter().filter(|p| !p.is_pip()) {
+        let name = package.name.to_lowercase();
+        if !seen.contains(&name) && removable.contains(&name) {
+            diff.removals.push(change(
+                &package.name,
+                Some(package),
+                None,
+                CondaChangeReason::NotInSpec,
+            ));
+        }
+    }
+    diff
+}
+
+/// Pip packages to add, change or remove. `removable` holds the normalized
+/// names of installed pip packages nothing else requires.
+pub fn diff_pip_packages(
+    spec: &CondaEnvSpec,
+    installed: &[InstalledCondaPackage],
+    removable: &HashSet<String>,
+) -> CondaPackageDiff {
+    let mut diff = CondaPackageDiff::default();
+    let mut seen = HashSet::new();
+    for wanted in &spec.pip {
+        if !seen.insert(wanted.name.clone()) {
+            continue;
+        }
+        let current = installed
+            .iter()
+            .find(|p| p.is_pip() && normalize_pip_name(&p.name) == wanted.name);
+        match current {
+            None => diff.additions.push(change(
+                &wanted.name,
+                None,
+                Some(wanted.raw.clone()),
+                CondaChangeReason::Missing,
+            )),
+            Some(current)
+                if !wanted
+                    .version
+                    .as_deref()
+                    .map(|v| version_matches(v, &current.version))
+                    .unwrap_or(true) =>
+            {
+                diff.changes.push(change(
+                    &wanted.name,
+                    Some(current),
+                    Some(wanted.raw.clone()),
+                    CondaChangeReason::VersionMismatch,
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+    for package in installed.iter().filter(|p| p.is_pip()) {
+        let name = normalize_pip_name(&package.name);
+        if !seen.contains(&name) && removable.contains(&name) {
+            diff.removals.push(change(
+                &package.name,
+                Some(package),
+                None,
+                CondaChangeReason::NotInSpec,
+            ));
+        }
+    }
+    diff
+}
+
+// ── conda-lock ──
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct RawLockMetadata {
+    #[serde(default)]
+    platforms: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: String,
+    /// `conda` or `pip`
+    #[serde(default)]
+    pub manager: String,
+    #[serde(default)]
+    pub platform: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RawCondaLock {
+    #[serde(default)]
+    version: Option<u32>,
+    #[serde(default)]
+    metadata: RawLockMetadata,
+    #[serde(default)]
+    package: Vec<LockedPackage>,
+}
+
+/// Unified `conda-lock.yml`
+#[derive(Debug, Clone)]
+pub struct CondaLock {
+    pub version: Option<u32>,
+    pub platforms: Vec<String>,
+    pub packages: Vec<LockedPackage>,
+}
+
+impl CondaLock {
+    /// Locked version of a conda package for `platform`, falling back to
+    /// the first platform that locks it
+    pub fn conda_version(&self, name: &str, platform: &str) -> Option<&str> {
+        let conda = |p: &&LockedPackage| p.name == name && p.manager != "pip";
+        self.packages
+            .iter()
+            .filter(conda)
+            .find(|p| p.platform == platform)
+            .or_else(|| self.packages.iter().find(conda))
+            .map(|p| p.version.as_str())
+    }
+}
+
+pub fn parse_conda_lock(content: &str) -> CogniaResult<CondaLock> {
+    let raw: RawCondaLock = serde_yaml::from_str(content)
+        .map_err(|e| CogniaError::Parse(format!("Invalid conda-lock file: {}", e)))?;
+    let mut platforms = raw.metadata.platforms;
+    if platforms.is_empty() {
+        for package in &raw.package {
+            if !package.platform.is_empty() && !platforms.contains(&package.platform) {
+                platforms.push(package.platform.clone());
+            }
+        }
+    }
+    Ok(CondaLock {
+        version: raw.version,
+        platforms,
+        packages: raw.package,
+    })
+}
+
+/// Conda subdir of the running platform (`linux-64`, `osx-arm64`, `win-64`, ...)
+pub fn current_conda_subdir() -> String {
+    let os = match std::env::consts::OS {
+        "macos" => "osx",
+        "windows" => "win",
+        other => other,
+    };
+    let arch = match std::env::consts::ARCH {
+        "x86_64" => "64",
+        "x86" => "32",
+        "aarch64" if os == "linux" => "aarch64",
+        "aarch64" => "arm64",
+        "powerpc64" => "ppc64le",
+        "s390x" => "s390x",
+        other => other,
+    };
+    format!("{}-{}", os, arch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn installed(name: &str, version: &str, channel: &str) -> InstalledCondaPackage {
+        InstalledCondaPackage {
+            name: name.to_string(),
+            version: version.to_string(),
+            channel: channel.to_string(),
+            build_string: "h_0".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_match_spec_forms() {
+        let spec = parse_match_spec("conda-forge::numpy>=1.24,<2").unwrap();
+        assert_eq!(spec.name, "numpy");
+        assert_eq!(spec.channel.as_deref(), Some("conda-forge"));
+        assert_eq!(spec.version.as_deref(), Some(">=1.24,<2"));
+
+        let spec = parse_match_spec("python=3.11=h_cpython").unwrap();
+        assert_eq!(spec.version.as_deref(), Some("=3.11"));
+        assert_eq!(spec.build.as_deref(), Some("h_cpython"));
+
+        let spec = parse_match_spec("libgcc-ng 12.2.0 h65d4601_19").unwrap();
+        assert_eq!(spec.version.as_deref(), Some("12.2.0"));
+        assert_eq!(spec.build.as_deref(), Some("h65d4601_19"));
+
+        let spec = parse_match_spec("python >=3.8, <3.12").unwrap();
+        assert_eq!(spec.version.as_deref(), Some(">=3.8,<3.12"));
+        assert_eq!(spec.build, None);
+
+        let spec = parse_match_spec("python >= 3.8").unwrap();
+        assert_eq!(spec.version.as_deref(), Some(">=3.8"));
+        assert_eq!(spec.build, None);
+
+        assert!(parse_match_spec("numpy[version='>=1']").is_none());
+    }
+
+    #[test]
+    fn test_version_matching() {
+        assert!(version_matches("=3.11", "3.11.8"));
+        assert!(!version_matches("=3.1", "3.11.8"));
+        assert!(version_matches(">=1.24,<2", "1.26.4"));
+        assert!(!version_matches(">=1.24,<2", "2.0.0"));
+        assert!(version_matches("1.2.*", "1.2"));
+        assert!(version_matches("==2.31.0", "2.31"));
+        assert!(version_matches("~=2.31", "2.32.1"));
+        assert!(!version_matches("~=2.31.0", "2.32.1"));
+        assert!(version_matches("<1.0|>=2", "2.1"));
+        assert!(compare_versions("1.0rc1", "1.0") == Ordering::Less);
+    }
+
+    #[test]
+    fn test_parse_environment_yml_with_pip_section() {
+        let spec = parse_environment_yml(
+            "name: ml\nchannels:\n  - conda-forge\n  - nodefaults\ndependencies:\n  - python=3.11\n  - numpy>=1.24\n  - pip\n  - pip:\n    - requests==2.31.0\n    - Flask_Login[extra]>=0.6 ; python_version > '3.8'\n    - -r requirements.txt\n",
+        )
+        .unwrap();
+        assert_eq!(spec.name.as_deref(), Some("ml"));
+        assert_eq!(spec.channels, vec!["conda-forge"]);
+        assert_eq!(spec.conda.len(), 3);
+        assert_eq!(spec.pip.len(), 2);
+        assert_eq!(spec.pip[1].name, "flask-login");
+        assert_eq!(spec.pip[1].version.as_deref(), Some(">=0.6"));
+        assert_eq!(spec.unsupported, vec!["pip: -r requirements.txt"]);
+    }
+
+    #[test]
+    fn test_diff_conda_and_pip_packages() {
+        let spec = parse_environment_yml(
+            "channels: [conda-forge]\ndependencies:\n  - python=3.11\n  - numpy>=1.24\n  - scipy\n  - pip:\n    - requests==2.31.0\n    - rich\n",
+        )
+        .unwrap();
+        let installed = vec![
+            installed("python", "3.11.8", "conda-forge"),
+            installed("numpy", "1.21.0", "conda-forge"),
+            installed("scipy", "1.11.0", "pkgs/main"),
+            installed("pandas", "2.1.0", "conda-forge"),
+            installed("openssl", "3.0.0", "conda-forge"),
+            installed("requests", "2.28.0", PYPI_CHANNEL),
+            installed("httpx", "0.27.0", PYPI_CHANNEL),
+        ];
+
+        let removable: HashSet<String> = ["pandas".to_string()].into();
+        let conda = diff_conda_packages(&spec, &installed, &removable);
+        assert!(conda.additions.is_empty());
+        let reasons: Vec<(&str, CondaChangeReason)> = conda
+            .changes
+            .iter()
+            .map(|c| (c.name.as_str(), c.reason))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                ("numpy", CondaChangeReason::VersionMismatch),
+                ("scipy", CondaChangeReason::ChannelMismatch),
+            ]
+        );
+        assert_eq!(conda.changes[1].spec.as_deref(), Some("conda-forge::scipy"));
+        assert_eq!(conda.removal_names(), vec!["pandas"]);
+
+        let pip_removable: HashSet<String> = ["httpx".to_string()].into();
+        let pip = diff_pip_packages(&spec, &installed, &pip_removable);
+        assert_eq!(pip.additions[0].name, "rich");
+        assert_eq!(pip.changes[0].name, "requests");
+        assert_eq!(pip.removal_names(), vec!["httpx"]);
+    }
+
+    #[test]
+    fn test_parse_conda_lock() {
+        let lock = parse_conda_lock(
+            "version: 1\nmetadata:\n  platforms: [linux-64, osx-arm64]\npackage:\n  - name: python\n    version: 3.11.8\n    manager: conda\n    platform: linux-64\n  - name: python\n    version: 3.11.9\n    manager: conda\n    platform: osx-arm64\n  - name: requests\n    version: 2.31.0\n    manager: pip\n    platform: linux-64\n",
+        )
+        .unwrap();
+        assert_eq!(lock.platforms, vec!["linux-64", "osx-arm64"]);
+        assert_eq!(lock.conda_version("python", "osx-arm64"), Some("3.11.9"));
+        assert_eq!(lock.conda_version("python", "win-64"), Some("3.11.8"));
+        assert_eq!(lock.conda_version("requests", "linux-64"), None);
+    }
+}
@@ -3,6 +3,7 @@
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// Custom detection rule for environment version detection
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,12 +35,51 @@ pub struct CustomDetectionRule {
     pub created_at: Option<String>,
     /// Last modified timestamp
     pub updated_at: Option<String>,
+    /// Command line the user approved for command-based extraction.
+    /// Approval is invalidated when the rule's command or arguments change.
+    #[serde(default)]
+    pub approved_command: Option<String>,
 }
 
 fn default_true() -> bool {
     true
 }
 
+/// Hard timeout for command-based extraction
+pub const COMMAND_EXTRACTION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Version-reporting tools that command-based rules may run without explicit
+/// approval, each with the only argument lists it may run with. Interpreters
+/// and build tools run arbitrary code given other arguments (`node -e`,
+/// `python -c`, `cargo run`), so any other command line needs approval.
+pub const COMMAND_EXTRACTION_ALLOWLIST: &[(&str, &[&[&str]])] = &[
+    ("bun", &[&["--version"], &["-v"]]),
+    ("cargo", &[&["--version"], &["-V"]]),
+    ("deno", &[&["--version"], &["-V"]]),
+    ("dotnet", &[&["--version"]]),
+    ("gcc", &[&["--version"], &["-dumpversion"]]),
+    ("go", &[&["version"], &["env", "GOVERSION"]]),
+    ("gradle", &[&["--version"], &["-v"]]),
+    ("java", &[&["-version"], &["--version"]]),
+    ("javac", &[&["-version"], &["--version"]]),
+    ("kotlin", &[&["-version"]]),
+    ("mvn", &[&["--version"], &["-v"]]),
+    ("node", &[&["--version"], &["-v"]]),
+    ("npm", &[&["--version"], &["-v"]]),
+    ("php", &[&["--version"], &["-v"]]),
+    ("pnpm", &[&["--version"], &["-v"]]),
+    ("python", &[&["--version"], &["-V"]]),
+    ("python3", &[&["--version"], &["-V"]]),
+    ("ruby", &[&["--version"], &["-v"]]),
+    ("rustc", &[&["--version"], &["-V"]]),
+    (
+        "terraform",
+        &[&["version"], &["version", "-json"], &["--version"], &["-v"]],
+    ),
+    ("yarn", &[&["--version"], &["-v"]]),
+    ("zig", &[&["version"]]),
+];
+
 /// Strategy for extracting version from matched file
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -100,6 +140,83 @@ pub enum ExtractionStrategy {
         /// Regex to extract version from output
         output_pattern: String,
     },
+    /// Run a command and extract version from stdout via JSON pointer and/or regex
+    CommandOutput {
+        /// Program to run (bare name or absolute path, never passed through a shell)
+        command: String,
+        /// Arguments
+        #[serde(default)]
+        args: Vec<String>,
+        /// RFC 6901 JSON pointer applied to stdout parsed as JSON (e.g. "/terraform_version")
+        #[serde(default)]
+        json_pointer: Option<String>,
+        /// Regex applied to stdout (or to the JSON pointer result when both are set)
+        #[serde(default)]
+        regex: Option<String>,
+    },
+}
+
+impl ExtractionStrategy {
+    /// Program and arguments for strategies that execute a command
+    pub fn command_line(&self) -> Option<(&str, &[String])> {
+        match self {
+            ExtractionStrategy::Command { cmd, args, .. } => Some((cmd.as_str(), args.as_slice())),
+            ExtractionStrategy::CommandOutput { command, args, .. } => {
+                Some((command.as_str(), args.as_slice()))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl CustomDetectionRule {
+    /// Canonical command line used to bind user approval to the exact invocation
+    pub fn command_signature(&self) -> Option<String> {
+        self.extraction
+            .command_line()
+            .map(|(program, args)| command_signature(program, args))
+    }
+
+    /// Whether the rule's command may run: allowlisted programs or an approval
+    /// that matches the current command line
+    pub fn command_permitted(&self) -> bool {
+        let Some((program, args)) = self.extraction.command_line() else {
+            return true;
+        };
+        if is_allowlisted_command(program, args) {
+            return true;
+        }
+        self.approved_command.is_some() && self.approved_command == self.command_signature()
+    }
+}
+
+/// Canonical form of a command line, used to bind an approval to the exact
+/// program and arguments
+pub fn command_signature(program: &str, args: &[String]) -> String {
+    std::iter::once(program)
+        .chain(args.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join("\u{1f}")
+}
+
+/// Check whether a command line is on the command extraction allowlist: an
+/// allowlisted program run with one of its version-query argument lists.
+/// Only bare program names qualify; explicit paths always need approval.
+pub fn is_allowlisted_command(program: &str, args: &[String]) -> bool {
+    if program.contains('/') || program.contains('\\') {
+        return false;
+    }
+    let name = program.strip_suffix(".exe").unwrap_or(program);
+    COMMAND_EXTRACTION_ALLOWLIST
+        .iter()
+        .filter(|(allowed, _)| allowed.eq_ignore_ascii_case(name))
+        .flat_map(|(_, arg_lists)| arg_lists.iter())
+        .any(|allowed_args| {
+            allowed_args
+                .iter()
+                .copied()
+                .eq(args.iter().map(String::as_str))
+        })
 }
 
 /// Optional version transformation after extraction
@@ -128,6 +245,36 @@ pub struct CustomDetectionResult {
     pub raw_version: String,
 }
 
+/// Rule left out of a detection run because it could not be evaluated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomDetectionSkipped {
+    pub rule_id: String,
+    pub rule_name: String,
+    pub env_type: String,
+    /// The rule runs a command the user has not approved
+    pub needs_approval: bool,
+    pub reason: String,
+}
+
+impl CustomDetectionSkipped {
+    fn new(rule: &CustomDetectionRule, error: &CogniaError) -> Self {
+        Self {
+            rule_id: rule.id.clone(),
+            rule_name: rule.name.clone(),
+            env_type: rule.env_type.clone(),
+            needs_approval: matches!(error, CogniaError::PermissionDenied(_)),
+            reason: error.to_string(),
+        }
+    }
+}
+
+/// Versions found by all custom rules, with the rules that were skipped
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CustomDetectionReport {
+    pub results: Vec<CustomDetectionResult>,
+    pub skipped: Vec<CustomDetectionSkipped>,
+}
+
 /// Manager for custom detection rules
 pub struct CustomDetectionManager {
     rules: Vec<CustomDetectionRule>,
@@ -144,19 +291,18 @@ pub fn new(config_dir: &Path) -> Self {
 
     /// Load rules from config file
     pub async fn load(&mut self) -> CogniaResult<()> {
-        if self.config_path.exists() {
-            let content = fs::read_file_string(&self.config_path).await?;
-            self.rules = serde_json::from_str(&content)
-                .map_err(|e| CogniaError::Config(format!("Failed to parse custom rules: {}", e)))?;
+        let loaded = fs::read_json_with_backup(&self.config_path)
+            .await
+            .map_err(|e| CogniaError::Config(format!("Failed to parse custom rules: {}", e)))?;
+        if let Some(rules) = loaded.into_value("Custom detection rules") {
+            self.rules = rules;
         }
         Ok(())
     }
 
     /// Save rules to config file
     pub async fn save(&self) -> CogniaResult<()> {
-        let content = serde_json::to_string_pretty(&self.rules)
-            .map_err(|e| CogniaError::Config(format!("Failed to serialize rules: {}", e)))?;
-        fs::write_file_string(&self.config_path, &content).await?;
+        fs::atomic_write_json(&self.config_path, &self.rules, true).await?;
         Ok(())
     }
 
@@ -177,20 +323,29 @@ pub fn get_rules_for_env(&self, env_type: &str) -> Vec<&CustomDetectionRule> {
     }
 
     /// Add a new rule
-    pub fn add_rule(&mut self, rule: CustomDetectionRule) -> CogniaResult<()> {
+    pub fn add_rule(&mut self, mut rule: CustomDetectionRule) -> CogniaResult<()> {
         if self.rules.iter().any(|r| r.id == rule.id) {
             return Err(CogniaError::Config(format!(
                 "Rule with ID '{}' already exists",
                 rule.id
             )));
         }
+        // New and imported rules never arrive pre-approved
+        rule.approved_command = None;
         self.rules.push(rule);
         Ok(())
     }
 
     /// Update an existing rule
-    pub fn update_rule(&mut self, rule: CustomDetectionRule) -> CogniaResult<()> {
+    pub fn update_rule(&mut self, mut rule: CustomDetectionRule) -> CogniaResult<()> {
         if let Some(existing) = self.rules.iter_mut().find(|r| r.id == rule.id) {
+            // Approval is only granted via `approve_command` and only carries over
+            // while the command line stays identical
+            let signature = rule.command_signature();
+            rule.approved_command = existing
+                .approved_command
+                .take()
+                .filter(|approved| Some(approved) == signature.as_ref());
             *existing = rule;
             Ok(())
         } else {
@@ -209,31 +364,69 @@ pub fn delete_rule(&mut self, rule_id: &str) -> CogniaResult<()> {
         }
     }
 
+    /// Re-add a rule from the trash as it was, including a command approval
+    /// the user granted before deleting it
+    pub fn restore_rule(&mut self, rule: CustomDetectionRule) -> CogniaResult<()> {
+        if self.rules.iter().any(|r| r.id == rule.id) {
+            return Err(CogniaError::Config(format!(
+                "Rule with ID '{}' already exists",
+                rule.id
+            )));
+        }
+        self.rules.push(rule);
+        Ok(())
+    }
+
     /// Get a rule by ID
     pub fn get_rule(&self, rule_id: &str) -> Option<&CustomDetectionRule> {
         self.rules.iter().find(|r| r.id == rule_id)
     }
 
-    /// Detect version using custom rules for a specific environment
-    pub async fn detect(
+    /// Record user approval for a rule's current command line
+    pub fn approve_command(&mut self, rule_id: &str) -> CogniaResult<()> {
+        let rule = self
+            .rules
+            .iter_mut()
+            .find(|r| r.id == rule_id)
+            .ok_or_else(|| CogniaError::Config(format!("Rule '{}' not found", rule_id)))?;
+        let signature = rule.command_signature().ok_or_else(|| {
+            CogniaError::Config(format!("Rule '{}' does not run a command", rule_id))
+        })?;
+        rule.approved_command = Some(signature);
+        rule.updated_at = Some(chrono::Utc::now().to_rfc3339());
+        Ok(())
+    }
+
+    /// Evaluate a single rule (which need not be registered) against a path
+    pub async fn test_rule(
         &self,
-        env_type: &str,
+        rule: &CustomDetectionRule,
         start_path: &Path,
     ) -> CogniaResult<Option<CustomDetectionResult>> {
+        self.evaluate_rule(rule, start_path).await
+    }
+
+    /// Detect version using custom rules for a specific environment. Rules
+    /// that cannot be evaluated (unapproved commands, unreadable files) are
+    /// skipped so the remaining rules still apply.
+    pub async fn detect(&self, env_type: &str, start_path: &Path) -> Option<CustomDetectionResult> {
         let rules = self.get_rules_for_env(env_type);
 
         for rule in rules {
-            if let Some(result) = self.evaluate_rule(rule, start_path).await? {
-                return Ok(Some(result));
+            match self.evaluate_rule(rule, start_path).await {
+                Ok(Some(result)) => return Some(result),
+                Ok(None) => {}
+                Err(e) => log::warn!("Skipping custom detection rule '{}': {}", rule.id, e),
             }
         }
 
-        Ok(None)
+        None
     }
 
-    /// Detect all versions in a directory using custom rules
-    pub async fn detect_all(&self, start_path: &Path) -> CogniaResult<Vec<CustomDetectionResult>> {
-        let mut results = Vec::new();
+    /// Detect all versions in a directory using custom rules. A rule that
+    /// cannot be evaluated is reported as skipped instead of failing the run.
+    pub async fn detect_all(&self, start_path: &Path) -> CustomDetectionReport {
+        let mut report = CustomDetectionReport::default();
         let mut detected_envs = std::collections::HashSet::new();
 
         // Sort all rules by priority
@@ -246,13 +439,17 @@ pub async fn detect_all(&self, start_path: &Path) -> CogniaResult<Vec<CustomDete
                 continue;
             }
 
-            if let Some(result) = self.evaluate_rule(rule, start_path).await? {
-                detected_envs.insert(rule.env_type.clone());
-                results.push(result);
+            match self.evaluate_rule(rule, start_path).await {
+                Ok(Some(result)) => {
+                    detected_envs.insert(rule.env_type.clone());
+                    report.results.push(result);
+                }
+                Ok(None) => {}
+                Err(e) => report.skipped.push(CustomDetectionSkipped::new(rule, &e)),
             }
         }
 
-        Ok(results)
+        report
     }
 
     /// Evaluate a single rule against a path
@@ -261,6 +458,25 @@ async fn evaluate_rule(
         rule: &CustomDetectionRule,
         start_path: &Path,
     ) -> CogniaResult<Option<CustomDetectionResult>> {
+        if rule.extraction.command_line().is_some() {
+            if !rule.command_permitted() {
+                let (program, _) = rule.extraction.command_line().unwrap_or_default();
+                return Err(CogniaError::PermissionDenied(format!(
+                    "Rule '{}' runs '{}', which is not allowlisted and has not been approved",
+                    rule.id, program
+                )));
+            }
+            // Command rules without file patterns run once in the start directory
+            if rule.file_patterns.is_empty() {
+                let cwd = if start_path.is_dir() {
+                    start_path
+                } else {
+                    start_path.parent().unwrap_or(start_path)
+                };
+                return self.build_result(rule, cwd, cwd.to_path_buf()).await;
+            }
+        }
+
         // Walk up directory tree
         let mut current = start_path.to_path_buf();
 
@@ -269,22 +485,8 @@ async fn evaluate_rule(
                 // Check if file matches pattern
                 if let Some(matched_file) = self.match_file_pattern(&current, pattern).await? {
                     // Try to extract version
-                    if let Some(raw_version) = self
-                        .extract_version(&matched_file, &rule.extraction)
-                        .await?
-                    {
-                        let version = self.transform_version(&raw_version, &rule.version_transform);
-
-                        if !version.is_empty() {
-                            return Ok(Some(CustomDetectionResult {
-                                rule_id: rule.id.clone(),
-                                rule_name: rule.name.clone(),
-                                env_type: rule.env_type.clone(),
-                                version,
-                                source_file: matched_file,
-                                raw_version,
-                            }));
-                        }
+                    if let Some(result) = self.build_result(rule, &current, matched_file).await? {
+                        return Ok(Some(result));
                     }
                 }
             }
@@ -297,6 +499,50 @@ async fn evaluate_rule(
         Ok(None)
     }
 
+    /// Extract and transform a version for a matched source
+    async fn build_result(
+        &self,
+        rule: &CustomDetectionRule,
+        dir: &Path,
+        source_file: PathBuf,
+    ) -> CogniaResult<Option<CustomDetectionResult>> {
+        let raw_version = match &rule.extraction {
+            ExtractionStrategy::CommandOutput {
+                command,
+                args,
+                json_pointer,
+                regex,
+            } => {
+                self.extract_command_output(
+                    command,
+                    args,
+                    json_pointer.as_deref(),
+                    regex.as_deref(),
+                    dir,
+                )
+                .await?
+            }
+            strategy => self.extract_version(&source_file, strategy).await?,
+        };
+
+        let Some(raw_version) = raw_version else {
+            return Ok(None);
+        };
+        let version = self.transform_version(&raw_version, &rule.version_transform);
+        if version.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(CustomDetectionResult {
+            rule_id: rule.id.clone(),
+            rule_name: rule.name.clone(),
+            env_type: rule.env_type.clone(),
+            version,
+            source_file,
+            raw_version,
+        }))
+    }
+
     /// Match file pattern in directory
     async fn match_file_pattern(&self, dir: &Path, pattern: &str) -> CogniaResult<Option<PathBuf>> {
         // Handle simple filename patterns
@@ -353,6 +599,22 @@ async fn extract_version(
                 args,
                 output_pattern,
             } => self.extract_command(cmd, args, output_pattern).await,
+            ExtractionStrategy::CommandOutput {
+                command,
+                args,
+                json_pointer,
+                regex,
+            } => {
+                let dir = file_path.parent().unwrap_or(file_path);
+                self.extract_command_output(
+                    command,
+                    args,
+                    json_pointer.as_deref(),
+                    regex.as_deref(),
+                    dir,
+                )
+                .await
+            }
         }
     }
 
@@ -568,7 +830,8 @@ async fn extract_command(
         use crate::platform::process;
 
         let args_ref: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-        let output = process::execute(cmd, &args_ref, None).await?;
+        let options = process::ProcessOptions::new().with_timeout(COMMAND_EXTRACTION_TIMEOUT);
+        let output = process::execute(cmd, &args_ref, Some(options)).await?;
 
         let text = if output.stdout.is_empty() {
             &output.stderr
@@ -588,6 +851,26 @@ async fn extract_command(
         Ok(None)
     }
 
+    async fn extract_command_output(
+        &self,
+        command: &str,
+        args: &[String],
+        json_pointer: Option<&str>,
+        regex: Option<&str>,
+        cwd: &Path,
+    ) -> CogniaResult<Option<String>> {
+        use crate::platform::process;
+
+        let args_ref: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+        let mut options = process::ProcessOptions::new().with_timeout(COMMAND_EXTRACTION_TIMEOUT);
+        if cwd.is_dir() {
+            options = options.with_cwd(cwd.to_string_lossy().to_string());
+        }
+        let output = process::execute(command, &args_ref, Some(options)).await?;
+
+        extract_from_command_output(&output.stdout, json_pointer, regex)
+    }
+
     /// Transform extracted version
     fn transform_version(&self, version: &str, transform: &Option<VersionTransform>) -> String {
         let mut result = version.trim().to_string();
@@ -621,6 +904,40 @@ fn transform_version(&self, version: &str, transform: &Option<VersionTransform>)
     }
 }
 
+/// Apply JSON pointer and/or regex extraction to captured command stdout
+fn extract_from_command_output(
+    stdout: &str,
+    json_pointer: Option<&str>,
+    regex: Option<&str>,
+) -> CogniaResult<Option<String>> {
+    let mut text = stdout.trim().to_string();
+
+    if let Some(pointer) = json_pointer {
+        let json: serde_json::Value = serde_json::from_str(&text)
+            .map_err(|e| CogniaError::Parse(format!("Command output is not JSON: {}", e)))?;
+        text = match json.pointer(pointer) {
+            Some(serde_json::Value::String(s)) => s.clone(),
+            Some(serde_json::Value::Number(n)) => n.to_string(),
+            _ => return Ok(None),
+        };
+    }
+
+    if let Some(pattern) = regex {
+        let re = Regex::new(pattern)
+            .map_err(|e| CogniaError::Config(format!("Invalid output pattern: {}", e)))?;
+        return Ok(re
+            .captures(&text)
+            .and_then(|caps| caps.name("version").or_else(|| caps.get(1)))
+            .map(|m| m.as_str().to_string()));
+    }
+
+    if text.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(text))
+    }
+}
+
 /// Simple glob pattern matching
 fn glob_match(pattern: &str, text: &str) -> bool {
     let pattern = pattern.replace('.', "\\.");
@@ -672,6 +989,7 @@ pub fn create_preset_rules() -> Vec<CustomDetectionRule> {
             tags: vec!["docker".to_string(), "preset".to_string()],
             created_at: None,
             updated_at: None,
+            approved_command: None,
         },
         // .nvmrc with lts/* format
         CustomDetectionRule {
@@ -690,6 +1008,7 @@ pub fn create_preset_rules() -> Vec<CustomDetectionRule> {
             tags: vec!["nvm".to_string(), "preset".to_string()],
             created_at: None,
             updated_at: None,
+            approved_command: None,
         },
         // GitHub Actions workflow Node version
         CustomDetectionRule {
@@ -713,6 +1032,7 @@ pub fn create_preset_rules() -> Vec<CustomDetectionRule> {
             tags: vec!["github-actions".to_string(), "ci".to_string(), "preset".to_string()],
             created_at: None,
             updated_at: None,
+            approved_command: None,
         },
         // Pipfile python_version
         CustomDetectionRule {
@@ -731,6 +1051,7 @@ pub fn create_preset_rules() -> Vec<CustomDetectionRule> {
             tags: vec!["pipenv".to_string(), "preset".to_string()],
             created_at: None,
             updated_at: None,
+            approved_command: None,
         },
         // .python-version with multiple versions
         CustomDetectionRule {
@@ -749,6 +1070,7 @@ pub fn create_preset_rules() -> Vec<CustomDetectionRule> {
             tags: vec!["pyenv".to_string(), "preset".to_string()],
             created_at: None,
             updated_at: None,
+            approved_command: None,
         },
         // Cargo.toml rust-version
         CustomDetectionRule {
@@ -766,6 +1088,7 @@ pub fn create_preset_rules() -> Vec<CustomDetectionRule> {
             tags: vec!["cargo".to_string(), "preset".to_string()],
             created_at: None,
             updated_at: None,
+            approved_command: None,
         },
         // gradle.properties java version
         CustomDetectionRule {
@@ -784,6 +1107,7 @@ pub fn create_preset_rules() -> Vec<CustomDetectionRule> {
             tags: vec!["gradle".to_string(), "preset".to_string()],
             created_at: None,
             updated_at: None,
+            approved_command: None,
         },
         // build.gradle sourceCompatibility
         CustomDetectionRule {
@@ -807,6 +1131,7 @@ pub fn create_preset_rules() -> Vec<CustomDetectionRule> {
             tags: vec!["gradle".to_string(), "preset".to_string()],
             created_at: None,
             updated_at: None,
+            approved_command: None,
         },
     ]
 }
@@ -905,6 +1230,7 @@ fn make_test_rule(id: &str, env_type: &str, priority: i32) -> CustomDetectionRul
             tags: vec![],
             created_at: None,
             updated_at: None,
+            approved_command: None,
         }
     }
 
@@ -973,6 +1299,24 @@ fn test_manager_delete_rule() {
         assert!(manager.list_rules().is_empty());
     }
 
+    #[test]
+    fn test_manager_restore_rule_keeps_approval() {
+        let mut manager = CustomDetectionManager::new(Path::new("/tmp/test"));
+        let mut rule = make_test_rule("rule-1", "node", 10);
+        rule.approved_command = Some("node --version".into());
+
+        manager.restore_rule(rule.clone()).unwrap();
+        assert_eq!(
+            manager
+                .get_rule("rule-1")
+                .unwrap()
+                .approved_command
+                .as_deref(),
+            Some("node --version")
+        );
+        assert!(manager.restore_rule(rule).is_err());
+    }
+
     #[test]
     fn test_manager_delete_nonexistent_rule() {
         let mut manager = CustomDetectionManager::new(Path::new("/tmp/test"));
@@ -1090,6 +1434,33 @@ fn test_transform_version_trims_whitespace() {
         assert_eq!(manager.transform_version("  18.17.1  ", &None), "18.17.1");
     }
 
+    #[tokio::test]
+    async fn test_manager_load_recovers_torn_rules_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manager = CustomDetectionManager::new(dir.path());
+        manager
+            .add_rule(make_test_rule("rule-1", "node", 10))
+            .unwrap();
+        manager.save().await.unwrap();
+        manager
+            .add_rule(make_test_rule("rule-2", "node", 5))
+            .unwrap();
+        manager.save().await.unwrap();
+
+        let path = dir.path().join("custom_detection_rules.json");
+        let content = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &content[..content.len() - 10]).unwrap();
+
+        let mut reloaded = CustomDetectionManager::new(dir.path());
+        reloaded.load().await.unwrap();
+        let ids: Vec<&str> = reloaded
+            .list_rules()
+            .iter()
+            .map(|r| r.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["rule-1"]);
+    }
+
     // ── Extract methods with tempfiles ──
 
     #[tokio::test]
@@ -1424,4 +1795,165 @@ fn test_version_transform_serde() {
         assert!(deser.strip_version_prefix);
         assert!(!deser.normalize_semver);
     }
+
+    fn make_command_rule(command: &str) -> CustomDetectionRule {
+        let mut rule = make_test_rule("cmd-rule", "terraform", 0);
+        rule.file_patterns = vec![];
+        rule.extraction = ExtractionStrategy::CommandOutput {
+            command: command.to_string(),
+            args: vec!["version".to_string(), "-json".to_string()],
+            json_pointer: Some("/terraform_version".to_string()),
+            regex: None,
+        };
+        rule
+    }
+
+    #[test]
+    fn test_legacy_rule_without_approval_field_loads() {
+        let json = r#"{
+            "id": "legacy",
+            "name": "Legacy",
+            "description": null,
+            "env_type": "node",
+            "file_patterns": [".nvmrc"],
+            "extraction": {"type": "plain_text", "strip_prefix": null, "strip_suffix": null},
+            "version_transform": null,
+            "created_at": null,
+            "updated_at": null
+        }"#;
+        let rule: CustomDetectionRule = serde_json::from_str(json).unwrap();
+        assert!(rule.approved_command.is_none());
+        assert!(rule.command_permitted());
+    }
+
+    #[test]
+    fn test_command_output_strategy_serde() {
+        let json = r#"{"type": "command_output", "command": "terraform", "args": ["version", "-json"], "json_pointer": "/terraform_version"}"#;
+        let strategy: ExtractionStrategy = serde_json::from_str(json).unwrap();
+        match strategy {
+            ExtractionStrategy::CommandOutput {
+                command,
+                args,
+                json_pointer,
+                regex,
+            } => {
+                assert_eq!(command, "terraform");
+                assert_eq!(args, vec!["version", "-json"]);
+                assert_eq!(json_pointer.as_deref(), Some("/terraform_version"));
+                assert!(regex.is_none());
+            }
+            _ => panic!("Expected CommandOutput"),
+        }
+    }
+
+    #[test]
+    fn test_command_allowlist_and_approval() {
+        let args = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        assert!(is_allowlisted_command(
+            "terraform",
+            &args(&["version", "-json"])
+        ));
+        assert!(is_allowlisted_command("node.exe", &args(&["--version"])));
+        assert!(!is_allowlisted_command(
+            "/usr/bin/node",
+            &args(&["--version"])
+        ));
+        assert!(!is_allowlisted_command("my-tool", &args(&["--version"])));
+        // Allowlisted programs with anything but a version query need approval
+        assert!(!is_allowlisted_command(
+            "node",
+            &args(&["-e", "process.exit()"])
+        ));
+        assert!(!is_allowlisted_command(
+            "python",
+            &args(&["-c", "print(1)"])
+        ));
+        assert!(!is_allowlisted_command("cargo", &args(&["run"])));
+        assert!(!is_allowlisted_command(
+            "node",
+            &args(&["--version", "-e", "1"])
+        ));
+        assert!(!is_allowlisted_command("node", &[]));
+
+        assert!(make_command_rule("terraform").command_permitted());
+        let mut manager = CustomDetectionManager::new(Path::new("/tmp/test"));
+        manager.add_rule(make_command_rule("my-tool")).unwrap();
+        assert!(!manager.get_rule("cmd-rule").unwrap().command_permitted());
+
+        manager.approve_command("cmd-rule").unwrap();
+        let approved = manager.get_rule("cmd-rule").unwrap().clone();
+        assert!(approved.command_permitted());
+
+        // Changing the command line drops the approval
+        let mut edited = approved.clone();
+        edited.extraction = ExtractionStrategy::CommandOutput {
+            command: "other-tool".to_string(),
+            args: vec![],
+            json_pointer: None,
+            regex: None,
+        };
+        manager.update_rule(edited).unwrap();
+        let rule = manager.get_rule("cmd-rule").unwrap();
+        assert!(rule.approved_command.is_none());
+        assert!(!rule.command_permitted());
+    }
+
+    #[tokio::test]
+    async fn test_detection_skips_unapproved_rule_and_keeps_file_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".terraform-version"), "1.7.5\n").unwrap();
+
+        let mut manager = CustomDetectionManager::new(dir.path());
+        let mut unapproved = make_command_rule("my-tool");
+        unapproved.priority = 10;
+        manager.add_rule(unapproved).unwrap();
+        let mut file_rule = make_test_rule("tf-file", "terraform", 0);
+        file_rule.file_patterns = vec![".terraform-version".to_string()];
+        manager.add_rule(file_rule).unwrap();
+
+        let report = manager.detect_all(dir.path()).await;
+        assert_eq!(report.results.len(), 1);
+        assert_eq!(report.results[0].rule_id, "tf-file");
+        assert_eq!(report.results[0].version, "1.7.5");
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].rule_id, "cmd-rule");
+        assert!(report.skipped[0].needs_approval);
+
+        let detected = manager.detect("terraform", dir.path()).await.unwrap();
+        assert_eq!(detected.rule_id, "tf-file");
+    }
+
+    #[tokio::test]
+    async fn test_unapproved_command_rule_is_rejected() {
+        let manager = CustomDetectionManager::new(Path::new("/tmp/test"));
+        let dir = tempfile::tempdir().unwrap();
+        let err = manager
+            .test_rule(&make_command_rule("my-tool"), dir.path())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("has not been approved"));
+    }
+
+    #[test]
+    fn test_extract_from_command_output_json_pointer_and_regex() {
+        let stdout = r#"{"terraform_version": "1.7.5", "platform": "linux_amd64"}"#;
+        assert_eq!(
+            extract_from_command_output(stdout, Some("/terraform_version"), None).unwrap(),
+            Some("1.7.5".to_string())
+        );
+        assert_eq!(
+            extract_from_command_output(stdout, Some("/missing"), None).unwrap(),
+            None
+        );
+        assert_eq!(
+            extract_from_command_output(
+                "Terraform v1.7.5\non linux",
+                None,
+                Some(r"v(?P<version>[\d.]+)")
+            )
+            .unwrap(),
+            Some("1.7.5".to_string())
+        );
+        assert!(extract_from_command_output("not json", Some("/x"), None).is_err());
+    }
 }
@@ -0,0 +1,545 @@
+use crate::core::custom_detection::{command_signature, is_allowlisted_command};
+use crate::core::environment::{candidate_provider_ids, provider_env_type_mapping};
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::fs;
+use crate::provider::SystemEnvironmentType;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Provider ids of user-defined environment types are `custom-<type id>`
+pub const CUSTOM_PROVIDER_PREFIX: &str = "custom-";
+
+/// Placeholders command templates may use
+pub const TEMPLATE_PLACEHOLDERS: &[&str] = &["{version}", "{install_dir}"];
+
+static PLACEHOLDER_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{[A-Za-z_]+\}").unwrap());
+
+/// User-defined environment type, managed through install/uninstall command
+/// templates and detected through custom detection rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomEnvTypeDefinition {
+    /// Environment type id (e.g. `terraform`), also the logical env type
+    pub id: String,
+    /// Human-readable name
+    pub display_name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// IDs of custom detection rules that detect the project version
+    #[serde(default)]
+    pub detection_rules: Vec<String>,
+    /// Where the list of installable versions comes from
+    pub version_source: VersionListSource,
+    /// Installs one version into `{install_dir}`
+    pub install: CommandTemplate,
+    /// Runs before the install directory is removed
+    #[serde(default)]
+    pub uninstall: Option<CommandTemplate>,
+    /// Executables inside the install directory, used for shims
+    #[serde(default)]
+    pub binaries: Vec<String>,
+    /// Command lines the user approved. Approvals are dropped when the
+    /// command they were granted for changes.
+    #[serde(default)]
+    pub approved_commands: Vec<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// Source of the versions a custom environment type can install
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum VersionListSource {
+    /// Fixed list, newest first
+    Static { versions: Vec<String> },
+    /// Release tags of a GitHub repository
+    GithubReleases {
+        /// `owner/name`
+        repo: String,
+        /// Prefix stripped from tags (a leading `v` is always stripped)
+        #[serde(default)]
+        tag_prefix: Option<String>,
+        #[serde(default)]
+        include_prereleases: bool,
+    },
+    /// One version per line of a command's stdout
+    Command {
+        command: String,
+        #[serde(default)]
+        args: Vec<String>,
+        /// Regex with a `version` (or first) capture group applied to each line
+        #[serde(default)]
+        regex: Option<String>,
+    },
+}
+
+/// Program and arguments with `{version}` / `{install_dir}` placeholders.
+/// Never passed through a shell.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandTemplate {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+impl CommandTemplate {
+    pub fn signature(&self) -> String {
+        command_signature(&self.command, &self.args)
+    }
+
+    /// Program and arguments with placeholders substituted
+    pub fn render(&self, version: &str, install_dir: &Path) -> (String, Vec<String>) {
+        let install_dir = install_dir.to_string_lossy();
+        let substitute = |value: &str| {
+            value
+                .replace("{version}", version)
+                .replace("{install_dir}", &install_dir)
+        };
+        (
+            substitute(&self.command),
+            self.args.iter().map(|arg| substitute(arg)).collect(),
+        )
+    }
+
+    fn unknown_placeholders(&self) -> Vec<String> {
+        std::iter::once(&self.command)
+            .chain(&self.args)
+            .flat_map(|value| PLACEHOLDER_RE.find_iter(value))
+            .map(|m| m.as_str().to_string())
+            .filter(|placeholder| !TEMPLATE_PLACEHOLDERS.contains(&placeholder.as_str()))
+            .collect()
+    }
+}
+
+impl CustomEnvTypeDefinition {
+    pub fn provider_id(&self) -> String {
+        format!("{}{}", CUSTOM_PROVIDER_PREFIX, self.id)
+    }
+
+    /// Every command this type runs: install, uninstall and the version list
+    pub fn command_lines(&self) -> Vec<(&str, &[String])> {
+        let mut commands = vec![(self.install.command.as_str(), self.install.args.as_slice())];
+        if let Some(uninstall) = &self.uninstall {
+            commands.push((uninstall.command.as_str(), uninstall.args.as_slice()));
+        }
+        if let VersionListSource::Command { command, args, .. } = &self.version_source {
+            commands.push((command.as_str(), args.as_slice()));
+        }
+        commands
+    }
+
+    /// Whether a command may run: allowlisted programs or an approval that
+    /// matches the exact command line, as for command-based detection rules
+    pub fn command_permitted(&self, program: &str, args: &[String]) -> bool {
+        is_allowlisted_command(program, args)
+            || self
+                .approved_commands
+                .contains(&command_signature(program, args))
+    }
+
+    /// Programs that still need the user's approval before they can run
+    pub fn unapproved_commands(&self) -> Vec<String> {
+        self.command_lines()
+            .into_iter()
+            .filter(|(program, args)| !self.command_permitted(program, args))
+            .map(|(program, _)| program.to_string())
+            .collect()
+    }
+
+    /// Error for a command that is neither allowlisted nor approved
+    pub fn ensure_command_permitted(&self, program: &str, args: &[String]) -> CogniaResult<()> {
+        if self.command_permitted(program, args) {
+            return Ok(());
+        }
+        Err(CogniaError::PermissionDenied(format!(
+            "Environment type '{}' runs '{}', which is not allowlisted and has not been approved",
+            self.id, program
+        )))
+    }
+
+    /// Problems that prevent saving the definition. `rule_ids` are the
+    /// custom detection rules that exist.
+    pub fn validate(&self, rule_ids: &[&str]) -> Vec<String> {
+        let mut issues = Vec::new();
+
+        let id_valid = self
+            .id
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+            && self.id.len() <= 32
+            && self
+                .id
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+        if !id_valid {
+            issues.push(format!(
+                "Id '{}' must be 1-32 lowercase letters, digits, '-' or '_'",
+                self.id
+            ));
+        } else if is_builtin_env_type(&self.id) {
+            issues.push(format!(
+                "Id '{}' is already used by a built-in environment type or provider",
+                self.id
+            ));
+        }
+        if self.display_name.trim().is_empty() {
+            issues.push("Display name is required".to_string());
+        }
+
+        for rule_id in &self.detection_rules {
+            if !rule_ids.contains(&rule_id.as_str()) {
+                issues.push(format!("Detection rule '{}' does not exist", rule_id));
+            }
+        }
+
+        match &self.version_source {
+            VersionListSource::Static { versions } => {
+                if versions.iter().all(|v| v.trim().is_empty()) {
+                    issues.push("Static version list is empty".to_string());
+                }
+            }
+            VersionListSource::GithubReleases { repo, .. } => {
+                let parts: Vec<&str> = repo.split('/').collect();
+                if parts.len() != 2 || parts.iter().any(|part| part.trim().is_empty()) {
+                    issues.push(format!("GitHub repository '{}' must be 'owner/name'", repo));
+                }
+            }
+            VersionListSource::Command { command, regex, .. } => {
+                if command.trim().is_empty() {
+                    issues.push("Version list command is required".to_string());
+                }
+                if let Some(Err(e)) = regex.as_deref().map(Regex::new) {
+                    issues.push(format!("Invalid version list regex: {}", e));
+                }
+            }
+        }
+
+        let templates = std::iter::once(("Install", &self.install))
+            .chain(self.uninstall.iter().map(|t| ("Uninstall", t)));
+        for (label, template) in templates {
+            if template.command.trim().is_empty() {
+                issues.push(format!("{} command is required", label));
+            }
+            for placeholder in template.unknown_placeholders() {
+                issues.push(format!(
+                    "{} command uses unknown placeholder {} (supported: {})",
+                    label,
+                    placeholder,
+                    TEMPLATE_PLACEHOLDERS.join(", ")
+                ));
+            }
+        }
+
+        for binary in &self.binaries {
+            if binary.trim().is_empty() || binary.contains(['/', '\\']) {
+                issues.push(format!("Binary '{}' must be a file name", binary));
+            }
+        }
+
+        issues
+    }
+}
+
+/// Whether `id` names a built-in environment type or provider
+fn is_builtin_env_type(id: &str) -> bool {
+    !candidate_provider_ids(id).is_empty()
+        || SystemEnvironmentType::all()
+            .iter()
+            .any(|env| env.env_type() == id)
+        || provider_env_type_mapping().contains_key(id)
+}
+
+/// Check that `version` is a single plain path component, since it names
+/// the directory under `versions/<type id>` that install and uninstall
+/// create and delete, and is passed to version managers as an argument.
+/// Versions come from the UI, release tags and command output, so none of
+/// them are trusted.
+pub fn validate_version(version: &str) -> CogniaResult<()> {
+    let valid = !version.trim().is_empty()
+        && version != "current"
+        && !version.contains(['/', '\\', ':'])
+        && !version.chars().any(|c| c.is_whitespace() || c.is_control())
+        && !version.contains("..")
+        && !version.starts_with(['.', '-'])
+        && !Path::new(version).is_absolute();
+    if valid {
+        Ok(())
+    } else {
+        Err(CogniaError::Provider(format!(
+            "Invalid version '{}': it must be a plain name without path separators",
+            version
+        )))
+    }
+}
+
+/// Manager for user-defined environment types
+pub struct CustomEnvTypeManager {
+    types: Vec<CustomEnvTypeDefinition>,
+    config_path: PathBuf,
+}
+
+impl CustomEnvTypeManager {
+    pub fn new(config_dir: &Path) -> Self {
+        Self {
+            types: Vec::new(),
+            config_path: config_dir.join("custom_env_types.json"),
+        }
+    }
+
+    /// Load definitions from the config file
+    pub async fn load(&mut self) -> CogniaResult<()> {
+        let loaded = fs::read_json_with_backup(&self.config_path)
+            .await
+            .map_err(|e| {
+                CogniaError::Config(format!("Failed to parse custom environment types: {}", e))
+            })?;
+        if let Some(types) = loaded.into_value("Custom environment types") {
+            self.types = types;
+        }
+        Ok(())
+    }
+
+    /// Save definitions to the config file
+    pub async fn save(&self) -> CogniaResult<()> {
+        fs::atomic_write_json(&self.config_path, &self.types, true).await?;
+        Ok(())
+    }
+
+    pub fn list(&self) -> &[CustomEnvTypeDefinition] {
+        &self.types
+    }
+
+    pub fn get(&self, id: &str) -> Option<&CustomEnvTypeDefinition> {
+        self.types.iter().find(|t| t.id == id)
+    }
+
+    /// Add a validated definition. New definitions never arrive pre-approved.
+    pub fn add(
+        &mut self,
+        mut definition: CustomEnvTypeDefinition,
+        rule_ids: &[&str],
+    ) -> CogniaResult<()> {
+        if self.get(&definition.id).is_some() {
+            return Err(CogniaError::Config(format!(
+                "Environment type '{}' already exists",
+                definition.id
+            )));
+        }
+        ensure_valid(&definition, rule_ids)?;
+        definition.approved_commands.clear();
+        self.types.push(definition);
+        Ok(())
+    }
+
+    /// Replace a definition, keeping approvals only for unchanged commands
+    pub fn update(
+        &mut self,
+        mut definition: CustomEnvTypeDefinition,
+        rule_ids: &[&str],
+    ) -> CogniaResult<()> {
+        ensure_valid(&definition, rule_ids)?;
+        let existing = self
+            .types
+            .iter_mut()
+            .find(|t| t.id == definition.id)
+            .ok_or_else(|| {
+                CogniaError::Config(format!("Environment type '{}' not found", definition.id))
+            })?;
+        let signatures: Vec<String> = definition
+            .command_lines()
+            .into_iter()
+            .map(|(program, args)| command_signature(program, args))
+            .collect();
+        definition.approved_commands = std::mem::take(&mut existing.approved_commands)
+            .into_iter()
+            .filter(|approved| signatures.contains(approved))
+            .collect();
+        *existing = definition;
+        Ok(())
+    }
+
+    pub fn delete(&mut self, id: &str) -> CogniaResult<CustomEnvTypeDefinition> {
+        let index =
+            self.types.iter().position(|t| t.id == id).ok_or_else(|| {
+                CogniaError::Config(format!("Environment type '{}' not found", id))
+            })?;
+        Ok(self.types.remove(index))
+    }
+
+    /// Record user approval for every command the type currently runs
+    pub fn approve_commands(&mut self, id: &str) -> CogniaResult<()> {
+        let definition =
+            self.types.iter_mut().find(|t| t.id == id).ok_or_else(|| {
+                CogniaError::Config(format!("Environment type '{}' not found", id))
+            })?;
+        definition.approved_commands = definition
+            .command_lines()
+            .into_iter()
+            .map(|(program, args)| command_signature(program, args))
+            .collect();
+        definition.updated_at = Some(chrono::Utc::now().to_rfc3339());
+        Ok(())
+    }
+}
+
+fn ensure_valid(definition: &CustomEnvTypeDefinition, rule_ids: &[&str]) -> CogniaResult<()> {
+    let issues = definition.validate(rule_ids);
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(CogniaError::Config(format!(
+            "Invalid environment type '{}': {}",
+            definition.id,
+            issues.join("; ")
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_definition(id: &str) -> CustomEnvTypeDefinition {
+        CustomEnvTypeDefinition {
+            id: id.to_string(),
+            display_name: "Terraform".to_string(),
+            description: None,
+            detection_rules: vec!["terraform-version".to_string()],
+            version_source: VersionListSource::GithubReleases {
+                repo: "hashicorp/terraform".to_string(),
+                tag_prefix: None,
+                include_prereleases: false,
+            },
+            install: CommandTemplate {
+                command: "tf-install".to_string(),
+                args: vec!["{version}".to_string(), "{install_dir}".to_string()],
+            },
+            uninstall: None,
+            binaries: vec!["terraform".to_string()],
+            approved_commands: Vec::new(),
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_accepts_complete_definition() {
+        let definition = make_definition("tf");
+        assert!(definition.validate(&["terraform-version"]).is_empty());
+    }
+
+    #[test]
+    fn test_validate_reports_every_problem() {
+        let mut definition = make_definition("Node");
+        definition.display_name = " ".to_string();
+        definition.version_source = VersionListSource::Command {
+            command: "list".to_string(),
+            args: Vec::new(),
+            regex: Some("(".to_string()),
+        };
+        definition.install.args.push("{prefix}".to_string());
+        definition.binaries.push("bin/tf".to_string());
+
+        let issues = definition.validate(&[]);
+        assert_eq!(issues.len(), 6, "{:?}", issues);
+        assert!(issues[0].contains("lowercase"));
+        assert!(issues.iter().any(|i| i.contains("terraform-version")));
+        assert!(issues.iter().any(|i| i.contains("{prefix}")));
+
+        let builtin = make_definition("node");
+        assert!(builtin.validate(&["terraform-version"])[0].contains("built-in"));
+        let provider = make_definition("fnm");
+        assert!(provider.validate(&["terraform-version"])[0].contains("built-in"));
+    }
+
+    #[test]
+    fn test_validate_version_rejects_paths() {
+        for version in ["1.9.0", "v2.0.0-rc.1", "nightly"] {
+            assert!(validate_version(version).is_ok(), "{}", version);
+        }
+        for version in [
+            "", " ", "..", "../x", "../..", "/tmp/x", "a/b", "a\\b", "C:x", ".hidden", "-rf",
+            "current", "17 ", "1.0\nx=y",
+        ] {
+            assert!(validate_version(version).is_err(), "{}", version);
+        }
+    }
+
+    #[test]
+    fn test_render_substitutes_placeholders() {
+        let definition = make_definition("tf");
+        let (program, args) = definition
+            .install
+            .render("1.9.0", Path::new("/opt/versions/tf/1.9.0"));
+        assert_eq!(program, "tf-install");
+        assert_eq!(args, vec!["1.9.0", "/opt/versions/tf/1.9.0"]);
+    }
+
+    #[test]
+    fn test_commands_require_allowlist_or_approval() {
+        let mut manager = CustomEnvTypeManager::new(Path::new("/tmp/test"));
+        let mut definition = make_definition("tf");
+        definition.approved_commands = vec![definition.install.signature()];
+        manager.add(definition, &["terraform-version"]).unwrap();
+
+        let stored = manager.get("tf").unwrap();
+        assert!(stored.approved_commands.is_empty());
+        assert_eq!(stored.unapproved_commands(), vec!["tf-install"]);
+        assert!(stored
+            .ensure_command_permitted("tf-install", &stored.install.args)
+            .is_err());
+        assert!(stored.command_permitted("terraform", &["version".to_string()]));
+        assert!(!stored.command_permitted("terraform", &["apply".to_string()]));
+
+        manager.approve_commands("tf").unwrap();
+        assert!(manager.get("tf").unwrap().unapproved_commands().is_empty());
+
+        // Changing the uninstall command keeps the install approval only
+        let mut updated = manager.get("tf").unwrap().clone();
+        updated.uninstall = Some(CommandTemplate {
+            command: "tf-remove".to_string(),
+            args: vec!["{install_dir}".to_string()],
+        });
+        manager.update(updated, &["terraform-version"]).unwrap();
+        assert_eq!(
+            manager.get("tf").unwrap().unapproved_commands(),
+            vec!["tf-remove"]
+        );
+    }
+
+    #[test]
+    fn test_manager_rejects_invalid_and_duplicate_types() {
+        let mut manager = CustomEnvTypeManager::new(Path::new("/tmp/test"));
+        assert!(manager.add(make_definition("tf"), &[]).is_err());
+        manager
+            .add(make_definition("tf"), &["terraform-version"])
+            .unwrap();
+        assert!(manager
+            .add(make_definition("tf"), &["terraform-version"])
+            .is_err());
+        assert!(manager
+            .update(make_definition("other"), &["terraform-version"])
+            .is_err());
+        assert_eq!(manager.delete("tf").unwrap().id, "tf");
+        assert!(manager.delete("tf").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_manager_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manager = CustomEnvTypeManager::new(dir.path());
+        manager
+            .add(make_definition("tf"), &["terraform-version"])
+            .unwrap();
+        manager.approve_commands("tf").unwrap();
+        manager.save().await.unwrap();
+
+        let mut reloaded = CustomEnvTypeManager::new(dir.path());
+        reloaded.load().await.unwrap();
+        let definition = reloaded.get("tf").unwrap();
+        assert_eq!(definition.provider_id(), "custom-tf");
+        assert!(definition.unapproved_commands().is_empty());
+    }
+}
@@ -0,0 +1,460 @@
+//! Parsing for `cognia://` links.
+//!
+//! Links only describe an action; nothing is executed until the user
+//! confirms it in the UI. Supported forms:
+//!
+//! - `cognia://install?env=node&version=20.11.0[&provider=fnm]`
+//!   (also `cognia://env/install?...`)
+//! - `cognia://package/install?provider=npm&name=typescript[&version=5.4.5]`
+//! - `cognia://plugin/install?id=my-team-tools`
+//! - `cognia://settings/appearance` or `cognia://settings?section=appearance`
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+pub const DEEP_LINK_SCHEME: &str = "cognia";
+
+const MAX_URL_LEN: usize = 2048;
+const MAX_ID_LEN: usize = 64;
+const MAX_NAME_LEN: usize = 214;
+const MAX_VERSION_LEN: usize = 64;
+
+/// Settings page sections a link may open
+pub const SETTINGS_SECTIONS: &[&str] = &[
+    "general",
+    "network",
+    "security",
+    "mirrors",
+    "appearance",
+    "updates",
+    "tray",
+    "envvar",
+    "paths",
+    "provider",
+    "backup",
+    "shortcuts",
+    "startup",
+    "system",
+];
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum DeepLinkError {
+    #[error("link is longer than {} characters", MAX_URL_LEN)]
+    TooLong,
+    #[error("unsupported scheme, expected {}://", DEEP_LINK_SCHEME)]
+    UnsupportedScheme,
+    #[error("unknown action '{0}'")]
+    UnknownAction(String),
+    #[error("missing parameter '{0}'")]
+    MissingParam(&'static str),
+    #[error("parameter '{0}' is given more than once")]
+    DuplicateParam(String),
+    #[error("invalid value for '{param}': {reason}")]
+    InvalidParam { param: &'static str, reason: String },
+}
+
+/// Action requested by a link
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    tag = "action",
+    rename_all = "snake_case",
+    rename_all_fields = "camelCase"
+)]
+pub enum DeepLinkAction {
+    EnvInstall {
+        env_type: String,
+        version: String,
+        provider_id: Option<String>,
+    },
+    PackageInstall {
+        provider: String,
+        name: String,
+        version: Option<String>,
+    },
+    PluginInstall {
+        store_id: String,
+    },
+    OpenSettings {
+        section: Option<String>,
+    },
+}
+
+/// A validated link, ready to be shown in the confirmation dialog
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeepLinkRequest {
+    /// The link in normalized form
+    pub url: String,
+    #[serde(flatten)]
+    pub action: DeepLinkAction,
+}
+
+/// Whether `arg` looks like a link for this app (e.g. a command-line argument)
+pub fn is_deep_link(arg: &str) -> bool {
+    arg.trim()
+        .get(..DEEP_LINK_SCHEME.len() + 3)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("cognia://"))
+}
+
+pub fn parse(url: &str) -> Result<DeepLinkRequest, DeepLinkError> {
+    let url = url.trim();
+    if url.len() > MAX_URL_LEN {
+        return Err(DeepLinkError::TooLong);
+    }
+    if !is_deep_link(url) {
+        return Err(DeepLinkError::UnsupportedScheme);
+    }
+    let rest = &url[DEEP_LINK_SCHEME.len() + 3..];
+    let rest = rest.split('#').next().unwrap_or_default();
+    let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let segments: Vec<String> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| segment.to_ascii_lowercase())
+        .collect();
+    let params = parse_query(query)?;
+
+    let action = match segments
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        ["install"] | ["env", "install"] => DeepLinkAction::EnvInstall {
+            env_type: identifier(&params, "env")?,
+            version: version(&params, "version")?,
+            provider_id: optional(&params, "provider", identifier)?,
+        },
+        ["package", "install"] => DeepLinkAction::PackageInstall {
+            provider: identifier(&params, "provider")?,
+            name: package_name(&params, "name")?,
+            version: optional(&params, "version", version)?,
+        },
+        ["plugin", "install"] => DeepLinkAction::PluginInstall {
+            store_id: plugin_id(&params, "id")?,
+        },
+        ["settings"] => DeepLinkAction::OpenSettings {
+            section: optional(&params, "section", settings_section)?,
+        },
+        ["settings", section] => {
+            let params = HashMap::from([("section".to_string(), section.to_string())]);
+            DeepLinkAction::OpenSettings {
+                section: Some(settings_section(&params, "section")?),
+            }
+        }
+        _ => return Err(DeepLinkError::UnknownAction(segments.join("/"))),
+    };
+
+    Ok(DeepLinkRequest {
+        url: to_url(&action),
+        action,
+    })
+}
+
+/// Normalized link for an action
+fn to_url(action: &DeepLinkAction) -> String {
+    let mut params: Vec<(&str, &str)> = Vec::new();
+    let path = match action {
+        DeepLinkAction::EnvInstall {
+            env_type,
+            version,
+            provider_id,
+        } => {
+            params.push(("env", env_type));
+            params.push(("version", version));
+            if let Some(provider) = provider_id {
+                params.push(("provider", provider));
+            }
+            "install"
+        }
+        DeepLinkAction::PackageInstall {
+            provider,
+            name,
+            version,
+        } => {
+            params.push(("provider", provider));
+            params.push(("name", name));
+            if let Some(version) = version {
+                params.push(("version", version));
+            }
+            "package/install"
+        }
+        DeepLinkAction::PluginInstall { store_id } => {
+            params.push(("id", store_id));
+            "plugin/install"
+        }
+        DeepLinkAction::OpenSettings { section } => {
+            if let Some(section) = section {
+                params.push(("section", section));
+            }
+            "settings"
+        }
+    };
+    let query = params
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, urlencoding::encode(value)))
+        .collect::<Vec<_>>()
+        .join("&");
+    if query.is_empty() {
+        format!("{}://{}", DEEP_LINK_SCHEME, path)
+    } else {
+        format!("{}://{}?{}", DEEP_LINK_SCHEME, path, query)
+    }
+}
+
+fn parse_query(query: &str) -> Result<HashMap<String, String>, DeepLinkError> {
+    let mut params = HashMap::new();
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let key = decode(key)?.to_ascii_lowercase();
+        let value = decode(value)?.trim().to_string();
+        if params.insert(key.clone(), value).is_some() {
+            return Err(DeepLinkError::DuplicateParam(key));
+        }
+    }
+    Ok(params)
+}
+
+fn decode(raw: &str) -> Result<String, DeepLinkError> {
+    urlencoding::decode(&raw.replace('+', " "))
+        .map(|value| value.into_owned())
+        .map_err(|_| DeepLinkError::InvalidParam {
+            param: "query",
+            reason: "not valid UTF-8".into(),
+        })
+}
+
+fn required<'a>(
+    params: &'a HashMap<String, String>,
+    param: &'static str,
+) -> Result<&'a str, DeepLinkError> {
+    params
+        .get(param)
+        .map(String::as_str)
+        .filter(|value| !value.is_empty())
+        .ok_or(DeepLinkError::MissingParam(param))
+}
+
+fn optional(
+    params: &HashMap<String, String>,
+    param: &'static str,
+    parse: fn(&HashMap<String, String>, &'static str) -> Result<String, DeepLinkError>,
+) -> Result<Option<String>, DeepLinkError> {
+    match params.get(param) {
+        Some(value) if !value.is_empty() => parse(params, param).map(Some),
+        _ => Ok(None),
+    }
+}
+
+fn invalid(param: &'static str, reason: &str) -> DeepLinkError {
+    DeepLinkError::InvalidParam {
+        param,
+        reason: reason.to_string(),
+    }
+}
+
+/// Rejects values that could be mistaken for a command-line flag
+fn check_common(value: &str, param: &'static str, max_len: usize) -> Result<(), DeepLinkError> {
+    if value.len() > max_len {
+        return Err(invalid(param, "too long"));
+    }
+    if value.starts_with('-') || value.starts_with('.') {
+        return Err(invalid(param, "must not start with '-' or '.'"));
+    }
+    if value.contains("..") {
+        return Err(invalid(param, "must not contain '..'"));
+    }
+    Ok(())
+}
+
+/// Environment type or provider id: lowercase letters, digits, `-`, `_` and `.`
+fn identifier(
+    params: &HashMap<String, String>,
+    param: &'static str,
+) -> Result<String, DeepLinkError> {
+    let value = required(params, param)?.to_ascii_lowercase();
+    check_common(&value, param, MAX_ID_LEN)?;
+    if !value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err(invalid(
+            param,
+            "only letters, digits, '-', '_' and '.' are allowed",
+        ));
+    }
+    Ok(value)
+}
+
+/// Marketplace ids are reverse-DNS style and keep their case
+fn plugin_id(
+    params: &HashMap<String, String>,
+    param: &'static str,
+) -> Result<String, DeepLinkError> {
+    let value = required(params, param)?;
+    check_common(value, param, MAX_ID_LEN * 2)?;
+    if !value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err(invalid(
+            param,
+            "only letters, digits, '-', '_' and '.' are allowed",
+        ));
+    }
+    Ok(value.to_string())
+}
+
+/// Package names may be scoped (`@types/node`) or path-like (`golang.org/x/tools`)
+fn package_name(
+    params: &HashMap<String, String>,
+    param: &'static str,
+) -> Result<String, DeepLinkError> {
+    let value = required(params, param)?;
+    check_common(value, param, MAX_NAME_LEN)?;
+    if !value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '@' | '/' | '+'))
+    {
+        return Err(invalid(param, "contains unsupported characters"));
+    }
+    Ok(value.to_string())
+}
+
+/// Exact versions, ranges and aliases such as `lts`
+fn version(params: &HashMap<String, String>, param: &'static str) -> Result<String, DeepLinkError> {
+    let value = required(params, param)?;
+    check_common(value, param, MAX_VERSION_LEN)?;
+    if !value.chars().all(|c| {
+        c.is_ascii_alphanumeric()
+            || matches!(c, '-' | '_' | '.' | '+' | '~' | '^' | '*' | '<' | '>' | '=')
+    }) {
+        return Err(invalid(param, "contains unsupported characters"));
+    }
+    Ok(value.to_string())
+}
+
+fn settings_section(
+    params: &HashMap<String, String>,
+    param: &'static str,
+) -> Result<String, DeepLinkError> {
+    let value = required(params, param)?.to_ascii_lowercase();
+    if !SETTINGS_SECTIONS.contains(&value.as_str()) {
+        return Err(invalid(param, "unknown settings section"));
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_env_install() {
+        let request = parse("cognia://install?env=Node&version=20.11.0").unwrap();
+        assert_eq!(
+            request.action,
+            DeepLinkAction::EnvInstall {
+                env_type: "node".into(),
+                version: "20.11.0".into(),
+                provider_id: None,
+            }
+        );
+        assert_eq!(request.url, "cognia://install?env=node&version=20.11.0");
+
+        let aliased = parse("COGNIA://env/install/?version=lts&env=node&provider=fnm").unwrap();
+        assert_eq!(
+            aliased.url,
+            "cognia://install?env=node&version=lts&provider=fnm"
+        );
+    }
+
+    #[test]
+    fn test_parse_package_install() {
+        let request =
+            parse("cognia://package/install?provider=npm&name=%40types%2Fnode&version=20.0.0")
+                .unwrap();
+        assert_eq!(
+            request.action,
+            DeepLinkAction::PackageInstall {
+                provider: "npm".into(),
+                name: "@types/node".into(),
+                version: Some("20.0.0".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_plugin_install_and_settings() {
+        let plugin = parse("cognia://plugin/install?id=my-team-tools").unwrap();
+        assert_eq!(
+            plugin.action,
+            DeepLinkAction::PluginInstall {
+                store_id: "my-team-tools".into()
+            }
+        );
+
+        let settings = parse("cognia://settings/Appearance").unwrap();
+        assert_eq!(
+            settings.action,
+            DeepLinkAction::OpenSettings {
+                section: Some("appearance".into())
+            }
+        );
+        assert_eq!(
+            parse("cognia://settings?section=tray").unwrap().url,
+            "cognia://settings?section=tray"
+        );
+    }
+
+    #[test]
+    fn test_rejects_malformed_links() {
+        assert_eq!(
+            parse("https://install?env=node&version=1").unwrap_err(),
+            DeepLinkError::UnsupportedScheme
+        );
+        assert_eq!(
+            parse("cognia://uninstall?env=node").unwrap_err(),
+            DeepLinkError::UnknownAction("uninstall".into())
+        );
+        assert_eq!(
+            parse("cognia://install?env=node").unwrap_err(),
+            DeepLinkError::MissingParam("version")
+        );
+        assert_eq!(
+            parse("cognia://install?env=node&env=python&version=1").unwrap_err(),
+            DeepLinkError::DuplicateParam("env".into())
+        );
+        assert!(matches!(
+            parse("cognia://install?env=node&version=--force").unwrap_err(),
+            DeepLinkError::InvalidParam {
+                param: "version",
+                ..
+            }
+        ));
+        assert!(matches!(
+            parse("cognia://package/install?provider=npm&name=a%20b").unwrap_err(),
+            DeepLinkError::InvalidParam { param: "name", .. }
+        ));
+        assert!(matches!(
+            parse("cognia://settings/secrets").unwrap_err(),
+            DeepLinkError::InvalidParam {
+                param: "section",
+                ..
+            }
+        ));
+        assert_eq!(
+            parse(&format!("cognia://install?env={}", "a".repeat(MAX_URL_LEN))).unwrap_err(),
+            DeepLinkError::TooLong
+        );
+    }
+
+    #[test]
+    fn test_is_deep_link() {
+        assert!(is_deep_link("cognia://install"));
+        assert!(is_deep_link(" Cognia://settings"));
+        assert!(!is_deep_link("--minimized"));
+        assert!(!is_deep_link("cognia:"));
+    }
+}
@@ -0,0 +1,534 @@
+use crate::core::shim::shim_usage_dir;
+use crate::core::EnvironmentInfo;
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::fs;
+use crate::resolver::{Version, VersionConstraint};
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tokio::sync::Mutex;
+
+const MAX_RECENT_PROJECT_DIRS: usize = 50;
+static USAGE_MUTATION_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+// ============================================================================
+// Usage log
+// ============================================================================
+
+/// Last-used timestamps for environment versions, recorded whenever a version
+/// is activated or launched through Cognia (launch, activate, terminal profiles)
+/// and merged with the markers version shims touch when run from a shell.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvUsageLog {
+    /// `env_type:version` -> RFC 3339 timestamp
+    #[serde(default)]
+    pub last_used: HashMap<String, String>,
+    /// Working directories seen in launches, most recent first.
+    #[serde(default)]
+    pub project_dirs: Vec<String>,
+}
+
+impl EnvUsageLog {
+    fn usage_path() -> Option<PathBuf> {
+        fs::get_cognia_dir().map(|dir| dir.join("state").join("env-usage.json"))
+    }
+
+    fn usage_key(env_type: &str, version: &str) -> String {
+        format!("{}:{}", env_type, version.trim_start_matches('v'))
+    }
+
+    pub async fn load() -> CogniaResult<Self> {
+        let path = Self::usage_path()
+            .ok_or_else(|| CogniaError::Config("Could not determine env usage path".into()))?;
+        let mut log = if fs::exists(&path).await {
+            let content = fs::read_file_string(&path).await?;
+            serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("Ignoring malformed env usage log at {:?}: {}", path, e);
+                Self::default()
+            })
+        } else {
+            Self::default()
+        };
+
+        if let Some(root_dir) = fs::get_cognia_dir() {
+            let shim_runs = tokio::task::spawn_blocking(move || shim_usage(&root_dir))
+                .await
+                .unwrap_or_default();
+            log.merge_shim_usage(shim_runs);
+        }
+        Ok(log)
+    }
+
+    pub async fn save(&self) -> CogniaResult<()> {
+        let path = Self::usage_path()
+            .ok_or_else(|| CogniaError::Config("Could not determine env usage path".into()))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let content = serde_json::to_string(self)
+            .map_err(|e| CogniaError::Config(format!("Failed to serialize env usage: {}", e)))?;
+        fs::write_file_atomic(&path, content.as_bytes()).await?;
+        Ok(())
+    }
+
+    pub fn last_used(&self, env_type: &str, version: &str) -> Option<&str> {
+        self.last_used
+            .get(&Self::usage_key(env_type, version))
+            .map(String::as_str)
+    }
+
+    pub fn touch(&mut self, env_type: &str, version: &str, cwd: Option<&Path>, timestamp: String) {
+        self.last_used
+            .insert(Self::usage_key(env_type, version), timestamp);
+        if let Some(cwd) = cwd {
+            let dir = cwd.display().to_string();
+            self.project_dirs.retain(|existing| existing != &dir);
+            self.project_dirs.insert(0, dir);
+            self.project_dirs.truncate(MAX_RECENT_PROJECT_DIRS);
+        }
+    }
+
+    /// Keep the later of the recorded and the shim timestamp for each version.
+    fn merge_shim_usage(&mut self, runs: Vec<(String, String, DateTime<Utc>)>) {
+        for (env_type, version, ran_at) in runs {
+            let key = Self::usage_key(&env_type, &version);
+            let recorded = self
+                .last_used
+                .get(&key)
+                .and_then(|t| DateTime::parse_from_rfc3339(t).ok());
+            if !recorded.is_some_and(|t| t >= ran_at) {
+                self.last_used.insert(key, ran_at.to_rfc3339());
+            }
+        }
+    }
+
+    /// Record a use of `env_type@version`. Failures are logged, never surfaced,
+    /// so usage tracking can't break a launch.
+    pub async fn record(env_type: &str, version: &str, cwd: Option<&Path>) {
+        let _guard = USAGE_MUTATION_LOCK.lock().await;
+        let result = async {
+            let mut log = Self::load().await?;
+            log.touch(env_type, version, cwd, chrono::Utc::now().to_rfc3339());
+            log.save().await
+        }
+        .await;
+        if let Err(e) = result {
+            log::debug!(
+                "Failed to record env usage for {}@{}: {}",
+                env_type,
+                version,
+                e
+            );
+        }
+    }
+}
+
+/// `(env_type, version, last run)` for every shim usage marker under
+/// `root_dir`. A `current` marker is attributed to the version
+/// `versions/<env_type>/current` points at now.
+fn shim_usage(root_dir: &Path) -> Vec<(String, String, DateTime<Utc>)> {
+    let mut runs = Vec::new();
+    let Ok(env_dirs) = std::fs::read_dir(shim_usage_dir(root_dir)) else {
+        return runs;
+    };
+    for env_dir in env_dirs.flatten() {
+        let env_type = env_dir.file_name().to_string_lossy().to_string();
+        let Ok(markers) = std::fs::read_dir(env_dir.path()) else {
+            continue;
+        };
+        for marker in markers.flatten() {
+            let Ok(modified) = marker.metadata().and_then(|m| m.modified()) else {
+                continue;
+            };
+            let mut version = marker.file_name().to_string_lossy().to_string();
+            if version == "current" {
+                let current = root_dir.join("versions").join(&env_type).join("current");
+                match std::fs::canonicalize(current)
+                    .ok()
+                    .and_then(|target| target.file_name().map(|n| n.to_string_lossy().to_string()))
+                {
+                    Some(target) => version = target,
+                    None => continue,
+                }
+            }
+            runs.push((env_type.clone(), version, DateTime::<Utc>::from(modified)));
+        }
+    }
+    runs
+}
+
+// ============================================================================
+// Disk usage
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvVersionDiskUsage {
+    pub version: String,
+    pub install_path: String,
+    pub size_bytes: u64,
+    pub is_current: bool,
+    pub installed_at: Option<String>,
+    pub last_used_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvTypeDiskUsage {
+    pub env_type: String,
+    pub provider_id: String,
+    pub total_bytes: u64,
+    pub versions: Vec<EnvVersionDiskUsage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvDiskUsageReport {
+    pub environments: Vec<EnvTypeDiskUsage>,
+    pub total_bytes: u64,
+    pub computed_at: String,
+}
+
+/// Walk every installed version directory in parallel (one blocking task per
+/// version) and attribute its size.
+pub async fn compute_disk_usage(
+    environments: &[EnvironmentInfo],
+    usage: &EnvUsageLog,
+) -> EnvDiskUsageReport {
+    let mut handles = Vec::new();
+    for env in environments {
+        for installed in &env.installed_versions {
+            let path = installed.install_path.clone();
+            handles.push(tokio::task::spawn_blocking(move || {
+                dir_size_blocking(&path)
+            }));
+        }
+    }
+    let mut sizes = futures::future::join_all(handles)
+        .await
+        .into_iter()
+        .map(|result| result.unwrap_or(0));
+
+    let mut report_envs = Vec::new();
+    for env in environments {
+        if env.installed_versions.is_empty() {
+            continue;
+        }
+        let mut versions: Vec<EnvVersionDiskUsage> = env
+            .installed_versions
+            .iter()
+            .map(|installed| EnvVersionDiskUsage {
+                version: installed.version.clone(),
+                install_path: installed.install_path.display().to_string(),
+                size_bytes: sizes.next().unwrap_or(0),
+                is_current: installed.is_current
+                    || env.current_version.as_deref() == Some(installed.version.as_str()),
+                installed_at: installed.installed_at.clone(),
+                last_used_at: usage
+                    .last_used(&env.env_type, &installed.version)
+                    .map(str::to_string),
+            })
+            .collect();
+        versions.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes));
+
+        report_envs.push(EnvTypeDiskUsage {
+            env_type: env.env_type.clone(),
+            provider_id: env.provider_id.clone(),
+            total_bytes: versions.iter().map(|v| v.size_bytes).sum(),
+            versions,
+        });
+    }
+    report_envs.sort_by(|a, b| b.total_bytes.cmp(&a.total_bytes));
+
+    EnvDiskUsageReport {
+        total_bytes: report_envs.iter().map(|e| e.total_bytes).sum(),
+        environments: report_envs,
+        computed_at: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
+fn dir_size_blocking(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|meta| meta.len())
+        .sum()
+}
+
+// ============================================================================
+// Reclaim suggestions
+// ============================================================================
+
+/// A project directory pinning `env_type` to `version` (via `.nvmrc`, etc.).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvProjectPin {
+    pub env_type: String,
+    pub version: String,
+    pub project_dir: String,
+    pub source: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvReclaimCandidate {
+    pub env_type: String,
+    pub provider_id: String,
+    pub version: String,
+    pub install_path: String,
+    pub estimated_savings: u64,
+    pub last_used_at: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvReclaimSuggestions {
+    pub candidates: Vec<EnvReclaimCandidate>,
+    pub total_savings: u64,
+    pub scanned_projects: Vec<String>,
+    pub pins: Vec<EnvProjectPin>,
+    pub computed_at: String,
+}
+
+/// Whether an installed version satisfies a project pin. Pins may be partial
+/// (`18` or `3.12` match `18.19.0` / `3.12.4`), may carry a `v` prefix, or
+/// may be ranges such as `^1.2`, `~3.11` or `>=18 <20`.
+pub fn version_matches_pin(installed: &str, pin: &str) -> bool {
+    let installed = installed.trim().trim_start_matches('v');
+    let pin = pin.trim().trim_start_matches('v');
+    if pin.is_empty() {
+        return false;
+    }
+    let prefix_match = installed == pin
+        || installed
+            .strip_prefix(pin)
+            .is_some_and(|rest| rest.starts_with('.') || rest.starts_with('-'));
+    if prefix_match || pin.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        return prefix_match;
+    }
+    // Ranges use the same matcher as dependency resolution
+    pin.parse::<VersionConstraint>()
+        .ok()
+        .zip(installed.parse::<Version>().ok())
+        .is_some_and(|(constraint, version)| constraint.matches(&version))
+}
+
+/// Rank versions that are neither active nor pinned by any scanned project.
+/// Never-used versions come first, then least recently used; ties go to the
+/// larger install.
+pub fn rank_reclaim_candidates(
+    report: &EnvDiskUsageReport,
+    pins: &[EnvProjectPin],
+) -> Vec<EnvReclaimCandidate> {
+    let mut candidates = Vec::new();
+    for env in &report.environments {
+        let env_pins: Vec<&str> = pins
+            .iter()
+            .filter(|pin| pin.env_type == env.env_type)
+            .map(|pin| pin.version.as_str())
+            .collect();
+        for version in &env.versions {
+            if version.is_current
+                || env_pins
+                    .iter()
+                    .any(|pin| version_matches_pin(&version.version, pin))
+            {
+                continue;
+            }
+            let reason = match &version.last_used_at {
+                Some(at) => format!("Not pinned by any known project; last used {}", at),
+                None => "Not pinned by any known project and never launched via Cognia".to_string(),
+            };
+            candidates.push(EnvReclaimCandidate {
+                env_type: env.env_type.clone(),
+                provider_id: env.provider_id.clone(),
+                version: version.version.clone(),
+                install_path: version.install_path.clone(),
+                estimated_savings: version.size_bytes,
+                last_used_at: version.last_used_at.clone(),
+                reason,
+            });
+        }
+    }
+
+    // RFC 3339 timestamps in UTC sort lexicographically
+    candidates.sort_by(|a, b| {
+        a.last_used_at
+            .cmp(&b.last_used_at)
+            .then(b.estimated_savings.cmp(&a.estimated_savings))
+    });
+    candidates
+}
+
+/// Deduplicated, existing project directories to scan for pins.
+pub fn collect_project_dirs(extra: &[String], usage: &EnvUsageLog) -> Vec<String> {
+    let mut seen = HashSet::new();
+    extra
+        .iter()
+        .chain(usage.project_dirs.iter())
+        .map(|dir| dir.trim().to_string())
+        .filter(|dir| !dir.is_empty() && Path::new(dir).is_dir())
+        .filter(|dir| seen.insert(dir.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage_report() -> EnvDiskUsageReport {
+        let version = |v: &str, size: u64, current: bool, last: Option<&str>| EnvVersionDiskUsage {
+            version: v.to_string(),
+            install_path: format!("/envs/node/{}", v),
+            size_bytes: size,
+            is_current: current,
+            installed_at: None,
+            last_used_at: last.map(str::to_string),
+        };
+        EnvDiskUsageReport {
+            environments: vec![EnvTypeDiskUsage {
+                env_type: "node".to_string(),
+                provider_id: "fnm".to_string(),
+                total_bytes: 0,
+                versions: vec![
+                    version("20.11.0", 100, true, None),
+                    version("18.19.0", 300, false, None),
+                    version("16.20.2", 200, false, Some("2026-01-01T00:00:00+00:00")),
+                    version("14.21.3", 50, false, None),
+                    version("22.1.0", 400, false, Some("2026-06-01T00:00:00+00:00")),
+                ],
+            }],
+            total_bytes: 0,
+            computed_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_version_matches_pin() {
+        assert!(version_matches_pin("18.19.0", "18"));
+        assert!(version_matches_pin("v18.19.0", "18.19"));
+        assert!(version_matches_pin("18.19.0", "v18.19.0"));
+        assert!(!version_matches_pin("180.1.0", "18"));
+        assert!(!version_matches_pin("18.19.0", ""));
+    }
+
+    #[test]
+    fn test_version_matches_range_pins() {
+        assert!(version_matches_pin("1.4.0", "^1.2"));
+        assert!(!version_matches_pin("2.0.0", "^1.2"));
+        assert!(!version_matches_pin("1.1.9", "^1.2"));
+
+        assert!(version_matches_pin("v18.19.0", ">=18 <20"));
+        assert!(version_matches_pin("19.0.0", ">=18 <20"));
+        assert!(!version_matches_pin("20.11.1", ">=18 <20"));
+        assert!(!version_matches_pin("16.20.2", ">=18 <20"));
+
+        assert!(version_matches_pin("3.11.9", "~3.11"));
+        assert!(!version_matches_pin("3.12.4", "~3.11"));
+
+        assert!(version_matches_pin("20.11.1", "20.x"));
+        assert!(!version_matches_pin("18.19.0", "system"));
+    }
+
+    #[test]
+    fn test_rank_reclaim_candidates_skips_current_and_pinned() {
+        let pins = vec![EnvProjectPin {
+            env_type: "node".to_string(),
+            version: "18".to_string(),
+            project_dir: "/work/app".to_string(),
+            source: ".nvmrc".to_string(),
+        }];
+        let candidates = rank_reclaim_candidates(&usage_report(), &pins);
+        let versions: Vec<&str> = candidates.iter().map(|c| c.version.as_str()).collect();
+        assert_eq!(versions, vec!["14.21.3", "16.20.2", "22.1.0"]);
+        assert_eq!(candidates[1].estimated_savings, 200);
+    }
+
+    #[test]
+    fn test_usage_log_touch_tracks_versions_and_dirs() {
+        let mut log = EnvUsageLog::default();
+        log.touch("node", "v20.1.0", Some(Path::new("/a")), "t1".into());
+        log.touch("node", "20.1.0", Some(Path::new("/b")), "t2".into());
+        log.touch("python", "3.12", Some(Path::new("/a")), "t3".into());
+        assert_eq!(log.last_used("node", "20.1.0"), Some("t2"));
+        assert_eq!(log.project_dirs, vec!["/a", "/b"]);
+    }
+
+    #[test]
+    fn test_shim_usage_markers_merge_into_last_used() {
+        let root = tempfile::tempdir().unwrap();
+        let markers = shim_usage_dir(root.path());
+        std::fs::create_dir_all(markers.join("node")).unwrap();
+        std::fs::write(markers.join("node").join("20.1.0"), "").unwrap();
+        std::fs::write(markers.join("node").join("current"), "").unwrap();
+        std::fs::create_dir_all(root.path().join("versions").join("node").join("18.0.0")).unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(
+            root.path().join("versions").join("node").join("18.0.0"),
+            root.path().join("versions").join("node").join("current"),
+        )
+        .unwrap();
+
+        let mut log = EnvUsageLog::default();
+        log.touch("node", "20.1.0", None, "2000-01-01T00:00:00+00:00".into());
+        log.touch("python", "3.12", None, "2000-01-01T00:00:00+00:00".into());
+        log.merge_shim_usage(shim_usage(root.path()));
+
+        assert_ne!(
+            log.last_used("node", "20.1.0"),
+            Some("2000-01-01T00:00:00+00:00")
+        );
+        assert_eq!(
+            log.last_used("python", "3.12"),
+            Some("2000-01-01T00:00:00+00:00")
+        );
+        #[cfg(unix)]
+        assert!(log.last_used("node", "18.0.0").is_some());
+        assert!(log.last_used("node", "current").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_compute_disk_usage_attributes_sizes() {
+        let dir = tempfile::tempdir().unwrap();
+        let big = dir.path().join("18.0.0");
+        let small = dir.path().join("20.0.0");
+        std::fs::create_dir_all(big.join("bin")).unwrap();
+        std::fs::create_dir_all(&small).unwrap();
+        std::fs::write(big.join("bin").join("node"), vec![0u8; 2048]).unwrap();
+        std::fs::write(small.join("node"), vec![0u8; 512]).unwrap();
+
+        let installed = |version: &str, path: PathBuf| crate::provider::InstalledVersion {
+            version: version.to_string(),
+            install_path: path,
+            size: None,
+            installed_at: None,
+            is_current: false,
+        };
+        let env = EnvironmentInfo {
+            env_type: "node".to_string(),
+            provider_id: "fnm".to_string(),
+            provider: "fnm".to_string(),
+            current_version: Some("20.0.0".to_string()),
+            installed_versions: vec![installed("20.0.0", small), installed("18.0.0", big)],
+            available: true,
+            total_size: 0,
+            version_count: 2,
+            compiler_metadata: None,
+            adopted_versions: vec![],
+        };
+
+        let report = compute_disk_usage(&[env], &EnvUsageLog::default()).await;
+        assert_eq!(report.total_bytes, 2560);
+        let versions = &report.environments[0].versions;
+        assert_eq!(versions[0].version, "18.0.0");
+        assert_eq!(versions[0].size_bytes, 2048);
+        assert!(versions[1].is_current);
+    }
+}
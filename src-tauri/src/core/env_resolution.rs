@@ -0,0 +1,610 @@
+use crate::core::environment::DetectedEnvironment;
+use crate::core::shim::ShimConfig;
+use serde::{Deserialize, Serialize};
+
+/// PATH directory markers owned by version managers.
+const PATH_MANAGER_MARKERS: &[(&str, &str)] = &[
+    (".pyenv/shims/", "pyenv"),
+    (".pyenv/versions/", "pyenv"),
+    (".nvm/versions/", "nvm"),
+    (".volta/bin/", "volta"),
+    (".asdf/shims/", "asdf"),
+    ("mise/shims/", "mise"),
+    ("fnm_multishells/", "fnm"),
+    (".fnm/", "fnm"),
+];
+
+/// Managers whose shims read project version files on every invocation.
+const PIN_AWARE_MANAGERS: &[&str] = &["pyenv", "asdf", "mise", "volta"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResolutionStepKind {
+    /// A version file or manifest pin found walking up from `cwd`
+    ProjectPin,
+    /// The provider's global/default version
+    GlobalVersion,
+    /// A directory Cognia prepends to PATH when launching the environment
+    CogniaActivation,
+    /// A PATH directory containing the binary
+    PathEntry,
+    /// A Cognia-managed shim for the binary
+    CogniaShim,
+    /// A version manager initialized from a shell profile
+    VersionManagerInit,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolutionStep {
+    pub kind: ResolutionStepKind,
+    pub label: String,
+    pub path: Option<String>,
+    pub version: Option<String>,
+    /// Version manager owning this step (`pyenv`, `nvm`, `cognia`, ...)
+    pub manager: Option<String>,
+    pub detail: String,
+    pub wins_in_shell: bool,
+    pub wins_in_cognia: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolutionVerdict {
+    pub binary_path: Option<String>,
+    pub version: Option<String>,
+    /// Indices into `steps` that decided this verdict
+    pub steps: Vec<usize>,
+    pub explanation: String,
+}
+
+/// Why a given version of an environment is (or isn't) picked up.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvResolutionReport {
+    pub env_type: String,
+    pub cwd: String,
+    pub binary: String,
+    pub steps: Vec<ResolutionStep>,
+    /// What a newly opened terminal runs
+    pub fresh_shell: ResolutionVerdict,
+    /// What Cognia-launched processes run
+    pub cognia: ResolutionVerdict,
+    /// Both verdicts have a version and they disagree
+    pub mismatch: bool,
+    pub warnings: Vec<String>,
+}
+
+/// A copy of the binary found on PATH.
+#[derive(Debug, Clone)]
+pub struct PathMatch {
+    pub dir: String,
+    pub binary_path: String,
+    /// Output of `<binary> --version` run from `cwd`, if probed
+    pub version: Option<String>,
+}
+
+/// A version manager init line found in a shell profile.
+#[derive(Debug, Clone)]
+pub struct ProfileManagerInit {
+    pub manager: String,
+    pub path: String,
+    /// 1-based
+    pub line: usize,
+    pub content: String,
+}
+
+/// Inputs for [`build_resolution_report`], gathered by the caller.
+pub struct ResolutionInput<'a> {
+    pub env_type: &'a str,
+    pub cwd: &'a str,
+    pub binary: &'a str,
+    /// Matches in PATH order
+    pub path_matches: &'a [PathMatch],
+    pub shim_dir: Option<&'a str>,
+    pub shims: &'a [ShimConfig],
+    /// Pins in resolution order; the first one wins
+    pub pins: &'a [DetectedEnvironment],
+    /// `(provider_id, version)`
+    pub global: Option<(&'a str, &'a str)>,
+    pub manager_inits: &'a [ProfileManagerInit],
+    /// The version Cognia activates for `cwd`
+    pub cognia_version: Option<&'a DetectedEnvironment>,
+    /// Binaries found in the directories Cognia prepends for that version
+    pub cognia_matches: &'a [String],
+}
+
+fn normalize_dir(dir: &str) -> String {
+    format!(
+        "{}/",
+        dir.replace('\\', "/").trim_end_matches('/').to_lowercase()
+    )
+}
+
+fn manager_for_dir(dir: &str, shim_dir: Option<&str>) -> Option<String> {
+    let normalized = normalize_dir(dir);
+    if shim_dir.is_some_and(|shim_dir| normalize_dir(shim_dir) == normalized) {
+        return Some("cognia".to_string());
+    }
+    PATH_MANAGER_MARKERS
+        .iter()
+        .find(|(marker, _)| normalized.contains(marker))
+        .map(|(_, manager)| manager.to_string())
+}
+
+/// Whether a version manager can change which `env_type` binary runs.
+pub fn manager_applies_to(manager: &str, env_type: &str) -> bool {
+    match manager {
+        "nvm" | "volta" | "fnm" => env_type == "node",
+        "pyenv" => env_type == "python",
+        _ => true,
+    }
+}
+
+fn extract_version(text: &str) -> Option<String> {
+    let start = text.find(|c: char| c.is_ascii_digit())?;
+    let version: String = text[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    Some(version.trim_end_matches('.').to_string())
+}
+
+/// Whether `actual` (e.g. `v20.11.1` or `Python 3.12.1`) satisfies an exact or
+/// prefix pin such as `20`, `3.12` or `20.x`. `None` when the pin is a range or
+/// alias that can't be compared.
+pub fn version_satisfies_pin(pin: &str, actual: &str) -> Option<bool> {
+    let pin = pin.trim().trim_start_matches('v');
+    let pin = pin
+        .strip_suffix(".*")
+        .or_else(|| pin.strip_suffix(".x"))
+        .unwrap_or(pin);
+    if pin.is_empty() || !pin.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        return None;
+    }
+    let actual = extract_version(actual)?;
+    Some(actual == pin || actual.starts_with(&format!("{}.", pin)))
+}
+
+pub fn build_resolution_report(input: &ResolutionInput) -> EnvResolutionReport {
+    let mut steps = Vec::new();
+    let mut warnings = Vec::new();
+
+    let pin_start = steps.len();
+    for (idx, pin) in input.pins.iter().enumerate() {
+        steps.push(ResolutionStep {
+            kind: ResolutionStepKind::ProjectPin,
+            label: pin.source.clone(),
+            path: pin
+                .source_path
+                .as_ref()
+                .map(|path| path.display().to_string()),
+            version: Some(pin.version.clone()),
+            manager: None,
+            detail: if idx == 0 {
+                "Nearest project pin".to_string()
+            } else {
+                format!("Shadowed by {}", input.pins[0].source)
+            },
+            wins_in_shell: false,
+            wins_in_cognia: false,
+        });
+    }
+
+    let global_step = input.global.map(|(provider_id, version)| {
+        steps.push(ResolutionStep {
+            kind: ResolutionStepKind::GlobalVersion,
+            label: format!("{} global", provider_id),
+            path: None,
+            version: Some(version.to_string()),
+            manager: Some(provider_id.to_string()),
+            detail: "Used when no project pin is found".to_string(),
+            wins_in_shell: false,
+            wins_in_cognia: false,
+        });
+        steps.len() - 1
+    });
+
+    let activation_start = steps.len();
+    for binary_path in input.cognia_matches {
+        steps.push(ResolutionStep {
+            kind: ResolutionStepKind::CogniaActivation,
+            label: "Cognia activation".to_string(),
+            path: Some(binary_path.clone()),
+            version: input
+                .cognia_version
+                .map(|detected| detected.version.clone()),
+            manager: Some("cognia".to_string()),
+            detail: "Prepended to PATH for Cognia-launched processes".to_string(),
+            wins_in_shell: false,
+            wins_in_cognia: false,
+        });
+    }
+
+    let path_start = steps.len();
+    for (idx, path_match) in input.path_matches.iter().enumerate() {
+        steps.push(ResolutionStep {
+            kind: ResolutionStepKind::PathEntry,
+            label: path_match.dir.clone(),
+            path: Some(path_match.binary_path.clone()),
+            version: path_match.version.clone(),
+            manager: manager_for_dir(&path_match.dir, input.shim_dir),
+            detail: if idx == 0 {
+                format!("First {} on PATH", input.binary)
+            } else {
+                format!("Shadowed by {}", input.path_matches[0].binary_path)
+            },
+            wins_in_shell: false,
+            wins_in_cognia: false,
+        });
+    }
+
+    for shim in input.shims {
+        steps.push(ResolutionStep {
+            kind: ResolutionStepKind::CogniaShim,
+            label: shim.binary_name.clone(),
+            path: Some(shim.target_path.display().to_string()),
+            version: shim.version.clone(),
+            manager: Some("cognia".to_string()),
+            detail: match input.shim_dir {
+                Some(dir) => format!("Shim in {}", dir),
+                None => "Cognia shim".to_string(),
+            },
+            wins_in_shell: false,
+            wins_in_cognia: false,
+        });
+    }
+
+    let relevant_inits: Vec<&ProfileManagerInit> = input
+        .manager_inits
+        .iter()
+        .filter(|init| manager_applies_to(&init.manager, input.env_type))
+        .collect();
+    for init in &relevant_inits {
+        steps.push(ResolutionStep {
+            kind: ResolutionStepKind::VersionManagerInit,
+            label: format!("{} init", init.manager),
+            path: Some(format!("{}:{}", init.path, init.line)),
+            version: None,
+            manager: Some(init.manager.clone()),
+            detail: init.content.trim().to_string(),
+            wins_in_shell: false,
+            wins_in_cognia: false,
+        });
+    }
+
+    let pin = input.pins.first();
+    let shell_manager = input
+        .path_matches
+        .first()
+        .and_then(|first| manager_for_dir(&first.dir, input.shim_dir));
+
+    let fresh_shell = match input.path_matches.first() {
+        None => ResolutionVerdict {
+            binary_path: None,
+            version: None,
+            steps: Vec::new(),
+            explanation: format!("{} was not found on PATH", input.binary),
+        },
+        Some(first) => {
+            let mut decided_by = vec![path_start];
+            let (version, explanation) = match shell_manager.as_deref() {
+                Some("cognia") => {
+                    let shim = input
+                        .shims
+                        .iter()
+                        .find(|shim| shim.binary_name == input.binary);
+                    (
+                        first
+                            .version
+                            .clone()
+                            .or_else(|| shim.and_then(|shim| shim.version.clone())),
+                        match shim {
+                            Some(shim) => format!(
+                                "Cognia shim {} runs {}",
+                                first.binary_path,
+                                shim.target_path.display()
+                            ),
+                            None => format!("Cognia shim {}", first.binary_path),
+                        },
+                    )
+                }
+                Some(manager) if PIN_AWARE_MANAGERS.contains(&manager) => match pin {
+                    Some(pin) => {
+                        decided_by.push(pin_start);
+                        (
+                            first.version.clone().or_else(|| Some(pin.version.clone())),
+                            format!(
+                                "{} shim resolves the project pin {} ({})",
+                                manager, pin.source, pin.version
+                            ),
+                        )
+                    }
+                    None => {
+                        decided_by.extend(global_step);
+                        (
+                            first.version.clone(),
+                            format!("{} shim resolves its global version", manager),
+                        )
+                    }
+                },
+                Some(manager) => {
+                    if let Some(pin) = pin {
+                        warnings.push(format!(
+                            "{} does not read {} on its own; run `{} use` in the shell",
+                            manager, pin.source, manager
+                        ));
+                    }
+                    (
+                        first.version.clone(),
+                        format!(
+                            "{} put {} on PATH for the version selected in its own state",
+                            manager, first.binary_path
+                        ),
+                    )
+                }
+                None => (
+                    first.version.clone(),
+                    format!("First {} on PATH: {}", input.binary, first.binary_path),
+                ),
+            };
+            ResolutionVerdict {
+                binary_path: Some(first.binary_path.clone()),
+                version,
+                steps: decided_by,
+                explanation,
+            }
+        }
+    };
+
+    let cognia = match (input.cognia_version, input.cognia_matches.first()) {
+        (Some(detected), Some(binary_path)) => {
+            let mut decided_by = vec![activation_start];
+            let pin_idx = input.pins.iter().position(|candidate| {
+                candidate.source == detected.source && candidate.source_path == detected.source_path
+            });
+            match pin_idx {
+                Some(idx) => decided_by.push(pin_start + idx),
+                None if detected.source_type == "global" => decided_by.extend(global_step),
+                None => {}
+            }
+            ResolutionVerdict {
+                binary_path: Some(binary_path.clone()),
+                version: Some(detected.version.clone()),
+                steps: decided_by,
+                explanation: format!(
+                    "Cognia activates {} {} from {} and prepends its bin directory to PATH",
+                    input.env_type, detected.version, detected.source
+                ),
+            }
+        }
+        (Some(detected), None) => {
+            warnings.push(format!(
+                "Cognia resolves {} {} from {} but found no {} in its bin directory",
+                input.env_type, detected.version, detected.source, input.binary
+            ));
+            ResolutionVerdict {
+                explanation: format!(
+                    "Cognia resolves {} {} but falls back to PATH: {}",
+                    input.env_type, detected.version, fresh_shell.explanation
+                ),
+                ..fresh_shell.clone()
+            }
+        }
+        (None, _) => ResolutionVerdict {
+            explanation: format!(
+                "Cognia resolves no {} version here and uses PATH: {}",
+                input.env_type, fresh_shell.explanation
+            ),
+            ..fresh_shell.clone()
+        },
+    };
+
+    for &idx in &fresh_shell.steps {
+        steps[idx].wins_in_shell = true;
+    }
+    for &idx in &cognia.steps {
+        steps[idx].wins_in_cognia = true;
+    }
+
+    if let (Some(pin), Some(actual)) = (pin, fresh_shell.version.as_deref()) {
+        if version_satisfies_pin(&pin.version, actual) == Some(false) {
+            warnings.push(format!(
+                "{} pins {} {} but a fresh shell runs {}",
+                pin.source, input.env_type, pin.version, actual
+            ));
+        }
+    }
+
+    if input.path_matches.len() > 1 {
+        warnings.push(format!(
+            "{} copies of {} on PATH; {} shadows the rest",
+            input.path_matches.len(),
+            input.binary,
+            input.path_matches[0].binary_path
+        ));
+    }
+
+    for init in relevant_inits {
+        if shell_manager.as_deref() != Some(init.manager.as_str()) {
+            warnings.push(format!(
+                "{} is initialized in {}:{} and can change PATH order in new shells",
+                init.manager, init.path, init.line
+            ));
+        }
+    }
+
+    let mismatch = match (fresh_shell.version.as_deref(), cognia.version.as_deref()) {
+        (Some(shell), Some(cognia)) => version_satisfies_pin(cognia, shell) == Some(false),
+        _ => false,
+    };
+
+    EnvResolutionReport {
+        env_type: input.env_type.to_string(),
+        cwd: input.cwd.to_string(),
+        binary: input.binary.to_string(),
+        steps,
+        fresh_shell,
+        cognia,
+        mismatch,
+        warnings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn pin(source: &str, version: &str, dir: &str) -> DetectedEnvironment {
+        DetectedEnvironment {
+            env_type: "node".into(),
+            version: version.into(),
+            source: source.into(),
+            source_path: Some(PathBuf::from(dir).join(source)),
+            source_type: "local".into(),
+            resolved_via: None,
+            resolved_version: None,
+            suggested_version: None,
+        }
+    }
+
+    fn path_match(dir: &str, binary: &str, version: Option<&str>) -> PathMatch {
+        PathMatch {
+            dir: dir.into(),
+            binary_path: format!("{}/{}", dir, binary),
+            version: version.map(str::to_string),
+        }
+    }
+
+    fn input<'a>(
+        path_matches: &'a [PathMatch],
+        pins: &'a [DetectedEnvironment],
+        inits: &'a [ProfileManagerInit],
+        cognia_matches: &'a [String],
+    ) -> ResolutionInput<'a> {
+        ResolutionInput {
+            env_type: "node",
+            cwd: "/work/app",
+            binary: "node",
+            path_matches,
+            shim_dir: Some("/home/u/.CogniaLauncher/shims"),
+            shims: &[],
+            pins,
+            global: Some(("fnm", "18.20.0")),
+            manager_inits: inits,
+            cognia_version: pins.first(),
+            cognia_matches,
+        }
+    }
+
+    #[test]
+    fn test_version_satisfies_pin() {
+        assert_eq!(version_satisfies_pin("20", "v20.11.1"), Some(true));
+        assert_eq!(version_satisfies_pin("3.12", "Python 3.12.1"), Some(true));
+        assert_eq!(version_satisfies_pin("20.x", "v20.1.0"), Some(true));
+        assert_eq!(version_satisfies_pin("2", "v20.1.0"), Some(false));
+        assert_eq!(version_satisfies_pin("v16.20.2", "v16.20.2"), Some(true));
+        assert_eq!(version_satisfies_pin(">=18", "v20.1.0"), None);
+        assert_eq!(version_satisfies_pin("lts/*", "v20.1.0"), None);
+    }
+
+    #[test]
+    fn test_shadowing_path_binary_is_reported() {
+        let matches = vec![
+            path_match("/usr/local/bin", "node", Some("v16.20.2")),
+            path_match("/home/u/.nvm/versions/node/v20.11.1/bin", "node", None),
+        ];
+        let pins = vec![
+            pin(".nvmrc", "20", "/work/app"),
+            pin(".node-version", "18", "/work"),
+        ];
+        let inits = vec![ProfileManagerInit {
+            manager: "nvm".into(),
+            path: "/home/u/.bashrc".into(),
+            line: 12,
+            content: ". \"$NVM_DIR/nvm.sh\"".into(),
+        }];
+        let cognia_matches = vec!["/cognia/node/20.11.1/bin/node".to_string()];
+        let report = build_resolution_report(&input(&matches, &pins, &inits, &cognia_matches));
+
+        assert_eq!(
+            report.fresh_shell.binary_path.as_deref(),
+            Some("/usr/local/bin/node")
+        );
+        assert_eq!(report.fresh_shell.version.as_deref(), Some("v16.20.2"));
+        assert_eq!(report.cognia.version.as_deref(), Some("20"));
+        assert!(report.mismatch);
+
+        let shell_winners: Vec<ResolutionStepKind> = report
+            .steps
+            .iter()
+            .filter(|s| s.wins_in_shell)
+            .map(|s| s.kind)
+            .collect();
+        assert_eq!(shell_winners, vec![ResolutionStepKind::PathEntry]);
+        let cognia_winners: Vec<&str> = report
+            .steps
+            .iter()
+            .filter(|s| s.wins_in_cognia)
+            .map(|s| s.label.as_str())
+            .collect();
+        assert_eq!(cognia_winners, vec![".nvmrc", "Cognia activation"]);
+        assert_eq!(report.steps[1].detail, "Shadowed by .nvmrc");
+
+        assert!(report
+            .warnings
+            .iter()
+            .any(|w| w == ".nvmrc pins node 20 but a fresh shell runs v16.20.2"));
+        assert!(report.warnings.iter().any(|w| w.starts_with("2 copies")));
+        assert!(report
+            .warnings
+            .iter()
+            .any(|w| w.contains("/home/u/.bashrc:12")));
+    }
+
+    #[test]
+    fn test_pin_aware_shim_follows_project_pin() {
+        let matches = vec![path_match("/home/u/.volta/bin", "node", Some("v20.11.1"))];
+        let pins = vec![pin("package.json (volta.node)", "20.11.1", "/work/app")];
+        let inits = vec![
+            ProfileManagerInit {
+                manager: "volta".into(),
+                path: "/home/u/.zshrc".into(),
+                line: 3,
+                content: "export VOLTA_HOME=\"$HOME/.volta\"".into(),
+            },
+            ProfileManagerInit {
+                manager: "pyenv".into(),
+                path: "/home/u/.zshrc".into(),
+                line: 4,
+                content: "eval \"$(pyenv init -)\"".into(),
+            },
+        ];
+        let report = build_resolution_report(&input(&matches, &pins, &inits, &[]));
+
+        assert!(report.fresh_shell.explanation.starts_with("volta shim"));
+        assert_eq!(report.fresh_shell.steps.len(), 2);
+        assert!(report.steps[0].wins_in_shell);
+        assert!(!report.mismatch);
+        // No Cognia bin directory match, so launches fall back to PATH.
+        assert_eq!(report.cognia.binary_path, report.fresh_shell.binary_path);
+        // pyenv init is irrelevant for node; volta is the active manager.
+        assert!(!report
+            .steps
+            .iter()
+            .any(|s| s.manager.as_deref() == Some("pyenv")));
+        assert!(!report.warnings.iter().any(|w| w.contains(".zshrc")));
+    }
+
+    #[test]
+    fn test_missing_binary() {
+        let report = build_resolution_report(&input(&[], &[], &[], &[]));
+        assert!(report.fresh_shell.binary_path.is_none());
+        assert_eq!(report.fresh_shell.explanation, "node was not found on PATH");
+        assert!(report.cognia.explanation.contains("uses PATH"));
+        assert!(!report.mismatch);
+        assert_eq!(report.steps.len(), 1);
+        assert_eq!(report.steps[0].kind, ResolutionStepKind::GlobalVersion);
+    }
+}
@@ -1,8 +1,11 @@
+use crate::core::custom_env_type::CUSTOM_PROVIDER_PREFIX;
+use crate::core::install_validation::{self, InstallValidation};
+use crate::core::version_sync::{self, AvailableVersions, VersionIndex, VersionSyncStats};
 use crate::error::{CogniaError, CogniaResult};
 use crate::platform::env::{current_platform, EnvModifications};
 use crate::provider::{
-    CppCompilerMetadata, EnvironmentProvider, InstalledVersion, ProviderRegistry,
-    SystemEnvironmentProvider, SystemEnvironmentType, VersionInfo,
+    CppCompilerMetadata, EnvironmentProvider, InstalledVersion, Provider, ProviderRegistry,
+    SystemEnvironmentProvider, SystemEnvironmentType, VersionInfo, VersionSource,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -29,6 +32,9 @@ pub struct EnvironmentInfo {
     pub version_count: usize,
     #[serde(default)]
     pub compiler_metadata: Option<CppCompilerMetadata>,
+    /// Versions adopted in place from another version manager.
+    #[serde(default)]
+    pub adopted_versions: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,6 +48,18 @@ pub struct DetectedEnvironment {
     /// Stable source category used by frontend mapping (`local`, `manifest`, `global`).
     #[serde(default = "default_detected_source_type")]
     pub source_type: String,
+    /// Version manager whose shims resolve the runtime on PATH (`pyenv`,
+    /// `volta`), in which case that manager decides the effective version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_via: Option<String>,
+    /// Version that actually runs when it differs from the pin's literal
+    /// value, e.g. the SDK a `global.json` rolls forward to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_version: Option<String>,
+    /// Newest available version satisfying a constraint pin that no
+    /// installed version satisfies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggested_version: Option<String>,
 }
 
 fn default_detected_source_type() -> String {
@@ -50,7 +68,7 @@ fn default_detected_source_type() -> String {
 
 /// TTL-cached available versions to avoid repeated network requests.
 pub struct VersionCache {
-    entries: RwLock<HashMap<String, (Instant, Vec<VersionInfo>)>>,
+    entries: RwLock<HashMap<String, (Instant, AvailableVersions)>>,
     ttl: Duration,
 }
 
@@ -62,7 +80,7 @@ pub fn new(ttl: Duration) -> Self {
         }
     }
 
-    pub async fn get(&self, key: &str) -> Option<Vec<VersionInfo>> {
+    pub async fn get(&self, key: &str) -> Option<AvailableVersions> {
         let entries = self.entries.read().await;
         if let Some((cached_at, versions)) = entries.get(key) {
             if cached_at.elapsed() < self.ttl {
@@ -72,7 +90,7 @@ pub async fn get(&self, key: &str) -> Option<Vec<VersionInfo>> {
         None
     }
 
-    pub async fn set(&self, key: String, versions: Vec<VersionInfo>) {
+    pub async fn set(&self, key: String, versions: AvailableVersions) {
         let mut entries = self.entries.write().await;
         entries.insert(key, (Instant::now(), versions));
     }
@@ -108,6 +126,7 @@ pub struct EnvironmentManager {
     ("uv", "python"),
     ("conda", "python"),
     ("pipx", "python"),
+    ("pixi", "python"),
     ("system-python", "python"),
     // Go providers
     ("goenv", "go"),
@@ -150,6 +169,12 @@ pub struct EnvironmentManager {
     // Lua providers
     ("luarocks", "lua"),
     ("system-lua", "lua"),
+    // Julia providers
+    ("juliaup", "julia"),
+    ("system-julia", "julia"),
+    // R providers
+    ("rig", "r"),
+    ("system-r", "r"),
     // C/C++ providers
     ("system-c", "c"),
     ("system-cpp", "cpp"),
@@ -178,7 +203,10 @@ pub fn provider_to_env_type(input: &str) -> String {
         return mapped.to_string();
     }
 
-    if let Some(stripped) = normalized.strip_prefix("system-") {
+    if let Some(stripped) = normalized
+        .strip_prefix("system-")
+        .or_else(|| normalized.strip_prefix(CUSTOM_PROVIDER_PREFIX))
+    {
         return stripped.to_string();
     }
 
@@ -220,14 +248,17 @@ pub fn env_type_to_default_provider(env_type: &str) -> String {
         "deno" => "deno".to_string(),
         "zig" => "zig".to_string(),
         "dart" => "fvm".to_string(),
+        "julia" => "juliaup".to_string(),
+        "r" => "rig".to_string(),
         "c" => "system-c".to_string(),
         "cpp" => "system-cpp".to_string(),
         "bun" => "system-bun".to_string(),
+        "cuda" => "system-cuda".to_string(),
         _ => logical,
     }
 }
 
-fn normalize_env_type(input: &str) -> String {
+pub(crate) fn normalize_env_type(input: &str) -> String {
     provider_to_env_type(input)
 }
 
@@ -264,9 +295,9 @@ pub(crate) fn candidate_provider_ids(env_type: &str) -> &'static [&'static str]
         "elixir" => &["mise", "asdf", "nix", "system-elixir"],
         "erlang" => &["mise", "asdf", "nix", "system-erlang"],
         "swift" => &["mise", "asdf", "nix", "system-swift"],
-        "julia" => &["mise", "asdf", "nix", "system-julia"],
+        "julia" => &["juliaup", "mise", "asdf", "nix", "system-julia"],
         "perl" => &["mise", "asdf", "nix", "system-perl"],
-        "r" => &["mise", "asdf", "nix", "system-r"],
+        "r" => &["rig", "mise", "asdf", "nix", "system-r"],
         "haskell" => &["mise", "asdf", "nix", "system-haskell"],
         "clojure" => &["mise", "asdf", "nix", "system-clojure"],
         "crystal" => &["mise", "asdf", "nix", "system-crystal"],
@@ -276,10 +307,43 @@ pub(crate) fn candidate_provider_ids(env_type: &str) -> &'static [&'static str]
         "fortran" => &["system-fortran"],
         "c" => &["system-c"],
         "cpp" => &["system-cpp"],
+        "cuda" => &["system-cuda"],
         _ => &[],
     }
 }
 
+/// Matcher for pins that are version constraints rather than exact versions
+fn pinned_constraint_matcher(env_type: &str, source: &str) -> Option<fn(&str, &str) -> bool> {
+    match (env_type, source) {
+        ("julia", "Project.toml (compat.julia)") => Some(crate::provider::juliaup::compat_matches),
+        ("r", "DESCRIPTION (R)") => Some(crate::provider::rig::constraint_matches),
+        _ => None,
+    }
+}
+
+/// Default version reported by a manager that owns the runtime on PATH
+async fn managed_default_version(logical: &str) -> Option<(&'static str, String)> {
+    match logical {
+        "julia" => {
+            let juliaup = crate::provider::juliaup::JuliaupProvider::new();
+            if !juliaup.is_available().await {
+                return None;
+            }
+            let version = juliaup.get_current_version().await.ok()??;
+            Some(("juliaup", version))
+        }
+        "r" => {
+            let rig = crate::provider::rig::RigProvider::new();
+            if !rig.is_available().await {
+                return None;
+            }
+            let version = rig.get_current_version().await.ok()??;
+            Some(("rig", version))
+        }
+        _ => None,
+    }
+}
+
 fn version_matches(installed: &str, requested: &str) -> bool {
     let mut installed = installed.trim();
     let mut requested = requested.trim();
@@ -388,12 +452,16 @@ pub async fn resolve_provider(
                 .get_environment_provider(env_type)
                 .filter(|_| registry.is_provider_enabled(env_type));
 
+            // User-defined environment types have a single provider
+            let custom_id = format!("{}{}", CUSTOM_PROVIDER_PREFIX, normalized_env_type);
             let candidates = candidate_provider_ids(&normalized_env_type)
                 .iter()
+                .copied()
+                .chain(std::iter::once(custom_id.as_str()))
                 .filter_map(|id| {
                     registry
                         .get_environment_provider(id)
-                        .map(|p| ((*id).to_string(), registry.is_provider_enabled(id), p))
+                        .map(|p| (id.to_string(), registry.is_provider_enabled(id), p))
                 })
                 .filter(|(_, _, p)| p.supported_platforms().contains(&platform))
                 .collect::<Vec<_>>();
@@ -463,7 +531,12 @@ pub async fn list_environments_with_concurrency(
         &self,
         max_concurrency: u32,
     ) -> CogniaResult<Vec<EnvironmentInfo>> {
-        let all_types = SystemEnvironmentType::all();
+        let custom_types = self.registry.read().await.custom_environment_types();
+        let all_types: Vec<String> = SystemEnvironmentType::all()
+            .iter()
+            .map(|env| env.env_type().to_string())
+            .chain(custom_types)
+            .collect();
         let mut futures = Vec::with_capacity(all_types.len());
 
         // Limit concurrent provider checks to avoid subprocess storms.
@@ -471,8 +544,7 @@ pub async fn list_environments_with_concurrency(
         let permits = (max_concurrency as usize).max(1).min(32);
         let semaphore = Arc::new(Semaphore::new(permits));
 
-        for env in &all_types {
-            let env_type = env.env_type().to_string();
+        for env_type in all_types {
             let registry = self.registry.clone();
             let sem = semaphore.clone();
             futures.push(tokio::spawn(async move {
@@ -494,6 +566,7 @@ pub async fn list_environments_with_concurrency(
                         total_size: 0,
                         version_count: 0,
                         compiler_metadata: None,
+                        adopted_versions: vec![],
                     }),
                 }
             }));
@@ -522,12 +595,27 @@ pub async fn get_environment(
         } else {
             None
         };
-        let installed = if available {
+        let mut installed = if available {
             provider.list_installed_versions().await.unwrap_or_default()
         } else {
             vec![]
         };
 
+        let adopted = super::version_import::AdoptedInstalls::load_default().await;
+        let mut adopted_versions = Vec::new();
+        for install in adopted.for_env(&logical) {
+            // A managed install of the same version takes precedence
+            if install.manager.owns_provider(&provider_id)
+                || installed.iter().any(|v| {
+                    v.version.trim_start_matches('v') == install.version.trim_start_matches('v')
+                })
+            {
+                continue;
+            }
+            adopted_versions.push(install.version.clone());
+            installed.push(install.to_installed_version());
+        }
+
         let total_size: u64 = installed.iter().filter_map(|v| v.size).sum();
         let version_count = installed.len();
         let compiler_metadata = environment_provider_cpp_metadata(&provider).await;
@@ -542,6 +630,7 @@ pub async fn get_environment(
             total_size,
             version_count,
             compiler_metadata,
+            adopted_versions,
         })
     }
 
@@ -563,6 +652,41 @@ pub async fn detect_version_with_sources(
         sources_in_priority: &[String],
     ) -> CogniaResult<Option<DetectedEnvironment>> {
         let logical = normalize_env_type(env_type);
+
+        // User-defined types detect through their own rules and version file
+        let custom_provider = self
+            .registry
+            .read()
+            .await
+            .get_environment_provider(&format!("{}{}", CUSTOM_PROVIDER_PREFIX, logical));
+        if let Some(provider) = custom_provider {
+            return Ok(provider.detect_version(start_path).await?.map(|detection| {
+                let is_global = matches!(detection.source, VersionSource::SystemDefault);
+                DetectedEnvironment {
+                    env_type: logical,
+                    version: detection.version,
+                    source: detection
+                        .source_path
+                        .as_deref()
+                        .and_then(Path::file_name)
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_else(|| "global".to_string()),
+                    source_path: detection.source_path,
+                    source_type: if is_global { "global" } else { "local" }.to_string(),
+                    resolved_via: None,
+                    resolved_version: None,
+                    suggested_version: None,
+                }
+            }));
+        }
+
+        let pyenv_shimmed =
+            logical == "python" && crate::provider::pyenv::is_pyenv_managed("python").await;
+        // FVM-managed Flutter (global `default` link or a project's `.fvm`
+        // SDK) versus a system Flutter install
+        let fvm_managed =
+            logical == "dart" && crate::provider::fvm::is_fvm_managed("flutter").await;
+
         if let Some(mut detected) =
             super::project_env_detect::detect_env_version(&logical, start_path, sources_in_priority)
                 .await?
@@ -573,9 +697,94 @@ pub async fn detect_version_with_sources(
                     &detected.source,
                 );
             }
+            if pyenv_shimmed {
+                detected.resolved_via = Some("pyenv".to_string());
+            }
+            if fvm_managed || (logical == "dart" && detected.source == ".fvmrc") {
+                detected.resolved_via = Some("fvm".to_string());
+            }
+            // global.json pins go through the SDK's rollForward policy, so the
+            // SDK that runs can differ from the literal version.
+            if logical == "dotnet" && detected.source.starts_with("global.json") {
+                if let Some(path) = detected.source_path.as_deref() {
+                    detected.resolved_version =
+                        crate::provider::dotnet::resolve_global_json_sdk(path).await;
+                }
+            }
+            if let Some(matcher) = pinned_constraint_matcher(&logical, &detected.source) {
+                detected.suggested_version = self
+                    .suggest_version_for_constraint(&logical, &detected.version, matcher)
+                    .await;
+            }
             return Ok(Some(detected));
         }
 
+        // Same for pyenv shims: `python` on PATH follows pyenv's global version.
+        if pyenv_shimmed {
+            let pyenv = crate::provider::pyenv::PyenvProvider::new();
+            if let Ok(Some(version)) = pyenv.get_current_version().await {
+                return Ok(Some(DetectedEnvironment {
+                    env_type: logical,
+                    version,
+                    source: "pyenv".to_string(),
+                    source_path: None,
+                    source_type: "global".to_string(),
+                    resolved_via: Some("pyenv".to_string()),
+                    resolved_version: None,
+                    suggested_version: None,
+                }));
+            }
+        }
+
+        if fvm_managed {
+            let fvm = crate::provider::fvm::FvmProvider::new();
+            if let Ok(Some(version)) = fvm.get_current_version().await {
+                return Ok(Some(DetectedEnvironment {
+                    env_type: logical,
+                    version,
+                    source: "fvm".to_string(),
+                    source_path: None,
+                    source_type: "global".to_string(),
+                    resolved_via: Some("fvm".to_string()),
+                    resolved_version: None,
+                    suggested_version: None,
+                }));
+            }
+        }
+
+        // Volta shims shadow any other Node manager's global version, so report
+        // them as the source instead of whichever provider would resolve below.
+        if logical == "node" && crate::provider::volta::is_volta_managed("node").await {
+            let volta = crate::provider::volta::VoltaProvider::new();
+            if let Ok(Some(version)) = volta.get_current_version().await {
+                return Ok(Some(DetectedEnvironment {
+                    env_type: logical,
+                    version,
+                    source: "volta".to_string(),
+                    source_path: None,
+                    source_type: "global".to_string(),
+                    resolved_via: Some("volta".to_string()),
+                    resolved_version: None,
+                    suggested_version: None,
+                }));
+            }
+        }
+
+        // juliaup and rig own the default `julia` / `R`, so their reported
+        // default wins over whatever binary is first on PATH.
+        if let Some((manager, version)) = managed_default_version(&logical).await {
+            return Ok(Some(DetectedEnvironment {
+                env_type: logical,
+                version,
+                source: manager.to_string(),
+                source_path: None,
+                source_type: "global".to_string(),
+                resolved_via: Some(manager.to_string()),
+                resolved_version: None,
+                suggested_version: None,
+            }));
+        }
+
         // Deterministic fallback: when no project-local or manifest pin is found,
         // fall back to the provider's global/default current version.
         if let Ok((_logical, _provider_id, provider)) =
@@ -588,6 +797,9 @@ pub async fn detect_version_with_sources(
                     source: "global".to_string(),
                     source_path: None,
                     source_type: "global".to_string(),
+                    resolved_via: None,
+                    resolved_version: None,
+                    suggested_version: None,
                 }));
             }
         }
@@ -595,14 +807,41 @@ pub async fn detect_version_with_sources(
         Ok(None)
     }
 
+    /// Newest available version matching `constraint`, or `None` when an
+    /// installed version already satisfies it.
+    async fn suggest_version_for_constraint(
+        &self,
+        logical: &str,
+        constraint: &str,
+        matcher: fn(&str, &str) -> bool,
+    ) -> Option<String> {
+        let (_, _, provider) = self.resolve_provider(logical, None, None).await.ok()?;
+        let installed = provider.list_installed_versions().await.ok()?;
+        if installed.iter().any(|v| matcher(constraint, &v.version)) {
+            return None;
+        }
+        // Providers list available versions newest first
+        provider
+            .get_versions(logical)
+            .await
+            .ok()?
+            .into_iter()
+            .find(|v| !v.yanked && matcher(constraint, &v.version))
+            .map(|v| v.version)
+    }
+
     pub async fn detect_all_versions(
         &self,
         start_path: &Path,
     ) -> CogniaResult<Vec<DetectedEnvironment>> {
-        let all_types = SystemEnvironmentType::all();
+        let custom_types = self.registry.read().await.custom_environment_types();
+        let all_types: Vec<String> = SystemEnvironmentType::all()
+            .iter()
+            .map(|env| env.env_type().to_string())
+            .chain(custom_types)
+            .collect();
         let mut detected = Vec::with_capacity(all_types.len());
-        for env in &all_types {
-            let env_type = env.env_type();
+        for env_type in &all_types {
             let logical = normalize_env_type(env_type);
             let sources = super::project_env_detect::default_enabled_detection_sources(&logical);
             if let Some(item) = self
@@ -706,6 +945,32 @@ pub async fn get_available_versions(
         provider.get_versions(&logical).await
     }
 
+    /// Bring `previous` up to date with upstream, fetching only what changed.
+    /// An empty cursor asks for the full list, which is used when `force_full`
+    /// is set or the last full sync is too old to trust for deletions.
+    pub async fn sync_available_versions(
+        &self,
+        env_type: &str,
+        provider_id: Option<&str>,
+        previous: Option<VersionIndex>,
+        force_full: bool,
+    ) -> CogniaResult<(String, VersionIndex, VersionSyncStats)> {
+        let (logical, resolved_id, provider) =
+            self.resolve_provider(env_type, provider_id, None).await?;
+        let now = chrono::Utc::now();
+        // An index built from another provider says nothing about this one
+        let previous = previous.filter(|index| index.provider_id == resolved_id);
+        let cursor = match &previous {
+            Some(index) if !force_full && !index.needs_full_sync(now) => index.cursor(),
+            _ => Default::default(),
+        };
+        let fetch = provider
+            .fetch_versions_incremental(&logical, &cursor)
+            .await?;
+        let (index, stats) = version_sync::apply_fetch(previous, &resolved_id, fetch, now);
+        Ok((logical, index, stats))
+    }
+
     /// Check whether a newer version is available for a specific environment.
     pub async fn check_env_updates(&self, env_type: &str) -> CogniaResult<EnvUpdateCheckResult> {
         let (logical, provider_id, provider) = self.resolve_provider(env_type, None, None).await?;
@@ -778,11 +1043,15 @@ pub async fn check_env_updates(&self, env_type: &str) -> CogniaResult<EnvUpdateC
 
     /// Check for updates across all known environment types.
     pub async fn check_all_env_updates(&self) -> CogniaResult<Vec<EnvUpdateCheckResult>> {
-        let all_types = SystemEnvironmentType::all();
+        let custom_types = self.registry.read().await.custom_environment_types();
+        let all_types: Vec<String> = SystemEnvironmentType::all()
+            .iter()
+            .map(|env| env.env_type().to_string())
+            .chain(custom_types)
+            .collect();
         let mut futures = Vec::with_capacity(all_types.len());
 
-        for env in &all_types {
-            let env_type = env.env_type().to_string();
+        for env_type in all_types {
             let registry = self.registry.clone();
             futures.push(tokio::spawn(async move {
                 let manager = EnvironmentManager::new(registry);
@@ -818,6 +1087,29 @@ pub async fn cleanup_versions(
         let current = provider.get_current_version().await.ok().flatten();
         let installed = provider.list_installed_versions().await.unwrap_or_default();
 
+        // Safety: refuse a cleanup that removes a working install and keeps
+        // no other working one
+        let validations =
+            install_validation::validate_installs(&logical, provider.as_ref(), &installed).await;
+        let (removing, retained): (Vec<_>, Vec<_>) = validations
+            .iter()
+            .partition(|v| versions_to_remove.contains(&v.version));
+        if removing.iter().any(|v| !v.is_broken()) && retained.iter().all(|v| v.is_broken()) {
+            let detail = if retained.is_empty() {
+                "no other version would remain".to_string()
+            } else {
+                let broken: Vec<String> = retained
+                    .iter()
+                    .map(|v| format!("{} ({})", v.version, v.summary()))
+                    .collect();
+                format!("every retained version is broken: {}", broken.join(", "))
+            };
+            return Err(CogniaError::Provider(format!(
+                "Refusing to clean up {}: it removes the last working version and {}",
+                logical, detail
+            )));
+        }
+
         let mut removed = Vec::new();
         let mut freed_bytes: u64 = 0;
         let mut errors = Vec::new();
@@ -861,6 +1153,99 @@ pub async fn cleanup_versions(
             errors,
         })
     }
+
+    /// Check every installed version of `env_type` for partial or broken installs
+    pub async fn validate_installs(&self, env_type: &str) -> CogniaResult<Vec<InstallValidation>> {
+        let (logical, _provider_id, provider) = self.resolve_provider(env_type, None, None).await?;
+        let installed = provider.list_installed_versions().await?;
+        Ok(install_validation::validate_installs(&logical, provider.as_ref(), &installed).await)
+    }
+
+    /// Remove installs that fail validation, along with the shims and
+    /// `current` links pointing at them. Versions that validate as healthy,
+    /// or whose problems are not conclusive, are skipped and reported in
+    /// `errors`.
+    pub async fn remove_broken_versions(
+        &self,
+        env_type: &str,
+        versions: &[String],
+        root_dir: &Path,
+    ) -> CogniaResult<EnvCleanupResult> {
+        let (logical, _provider_id, provider) = self.resolve_provider(env_type, None, None).await?;
+        let installed = provider.list_installed_versions().await?;
+
+        let mut removed = Vec::new();
+        let mut freed_bytes: u64 = 0;
+        let mut errors = Vec::new();
+
+        for version in versions {
+            let Some(install) = installed.iter().find(|v| v.version == *version) else {
+                errors.push(format!("Skipped {}: not installed", version));
+                continue;
+            };
+            let bin_dirs = install_validation::provider_bin_dirs(provider.as_ref(), version);
+            let validation =
+                install_validation::validate_install(&logical, install, bin_dirs.as_deref()).await;
+            if !validation.is_broken() {
+                errors.push(format!("Skipped {}: install is healthy", version));
+                continue;
+            }
+            if !validation.removable {
+                errors.push(format!(
+                    "Skipped {}: {} (not certain enough to remove automatically)",
+                    version,
+                    validation.summary()
+                ));
+                continue;
+            }
+
+            let uninstalled = provider
+                .uninstall(crate::provider::UninstallRequest {
+                    name: logical.clone(),
+                    version: Some(version.clone()),
+                    force: true,
+                })
+                .await;
+            // Half-extracted installs often confuse the provider's own
+            // uninstall; fall back to deleting the version directory.
+            if let Err(e) = uninstalled {
+                if install.install_path.exists() {
+                    if !install_validation::is_version_directory(&install.install_path, version) {
+                        errors.push(format!("Failed to remove {}: {}", version, e));
+                        continue;
+                    }
+                    if let Err(e) = tokio::fs::remove_dir_all(&install.install_path).await {
+                        errors.push(format!("Failed to remove {}: {}", version, e));
+                        continue;
+                    }
+                }
+            }
+
+            if let Err(e) = install_validation::remove_version_pointers(
+                root_dir,
+                &logical,
+                version,
+                &install.install_path,
+            )
+            .await
+            {
+                errors.push(format!("Removed {} but kept its shims: {}", version, e));
+            }
+
+            let size = install.size.unwrap_or(0);
+            removed.push(CleanedVersion {
+                version: version.clone(),
+                size,
+            });
+            freed_bytes += size;
+        }
+
+        Ok(EnvCleanupResult {
+            removed,
+            freed_bytes,
+            errors,
+        })
+    }
 }
 
 /// Result of checking a single environment for version updates.
@@ -896,7 +1281,7 @@ pub struct CleanedVersion {
 ///
 /// Tries the `semver` crate first (handles pre-release, build metadata correctly).
 /// Falls back to numeric-only comparison for non-semver strings (e.g. `nightly-2025-01-01`).
-fn compare_semver(a: &str, b: &str) -> i32 {
+pub(crate) fn compare_semver(a: &str, b: &str) -> i32 {
     fn clean(s: &str) -> &str {
         let s = s.trim();
         s.strip_prefix('v').unwrap_or(s)
@@ -1179,6 +1564,19 @@ async fn resolve_provider_skips_disabled_direct_provider() {
         assert_eq!(provider_id, "system-deno");
     }
 
+    #[tokio::test]
+    async fn custom_env_type_resolves_to_its_provider() {
+        let mut registry = ProviderRegistry::new();
+        registry.register_environment_provider(Arc::new(DummyEnvProvider::new("custom-tf", true)));
+
+        let manager = EnvironmentManager::new(Arc::new(RwLock::new(registry)));
+        let (logical, provider_id, _provider) =
+            manager.resolve_provider("tf", None, None).await.unwrap();
+        assert_eq!(logical, "tf");
+        assert_eq!(provider_id, "custom-tf");
+        assert_eq!(provider_to_env_type("custom-tf"), "tf");
+    }
+
     #[tokio::test]
     async fn detect_version_falls_back_to_global_when_no_project_source_matches() {
         let mut registry = ProviderRegistry::new();
@@ -1329,6 +1727,30 @@ fn candidate_provider_ids_dart() {
         assert!(fvm_pos < sys_pos, "fvm should be listed before system-dart");
     }
 
+    #[test]
+    fn candidate_provider_ids_julia_and_r_prefer_dedicated_managers() {
+        assert_eq!(candidate_provider_ids("julia")[0], "juliaup");
+        assert_eq!(candidate_provider_ids("r")[0], "rig");
+        assert_eq!(provider_to_env_type("juliaup"), "julia");
+        assert_eq!(provider_to_env_type("rig"), "r");
+        assert_eq!(env_type_to_default_provider("julia"), "juliaup");
+        assert_eq!(env_type_to_default_provider("r"), "rig");
+    }
+
+    #[test]
+    fn pinned_constraint_matcher_only_for_constraint_sources() {
+        let julia = pinned_constraint_matcher("julia", "Project.toml (compat.julia)").unwrap();
+        assert!(julia("1.6", "1.10.2"));
+        assert!(!julia("1.6", "2.0.0"));
+
+        let r = pinned_constraint_matcher("r", "DESCRIPTION (R)").unwrap();
+        assert!(r(">= 4.1.0", "4.3.2"));
+        assert!(!r(">= 4.1.0", "4.0.5"));
+
+        assert!(pinned_constraint_matcher("julia", ".julia-version").is_none());
+        assert!(pinned_constraint_matcher("node", "DESCRIPTION (R)").is_none());
+    }
+
     #[test]
     fn candidate_provider_ids_unknown_returns_empty() {
         let candidates = candidate_provider_ids("unknown_language");
@@ -138,6 +138,18 @@ pub fn new() -> Self {
 
     /// Fetch EOL data for a product, using cache when available.
     pub async fn get_eol_data(&self, env_type: &str) -> CogniaResult<Vec<EolCycleInfo>> {
+        self.get_eol_data_with_staleness(env_type)
+            .await
+            .map(|(cycles, _)| cycles)
+    }
+
+    /// Like [`get_eol_data`](Self::get_eol_data), but when the API is unreachable
+    /// an expired cache entry is returned instead of an error. The flag is `true`
+    /// when the returned data is such a stale entry.
+    pub async fn get_eol_data_with_staleness(
+        &self,
+        env_type: &str,
+    ) -> CogniaResult<(Vec<EolCycleInfo>, bool)> {
         let product = env_type_to_product(env_type).ok_or_else(|| {
             CogniaError::Provider(format!(
                 "No EOL data available for environment type: {}",
@@ -146,17 +158,27 @@ pub async fn get_eol_data(&self, env_type: &str) -> CogniaResult<Vec<EolCycleInf
         })?;
 
         // Check cache first
-        {
+        let cached = {
             let cache = self.data.read().await;
-            if let Some((data, fetched_at)) = cache.get(product) {
-                if fetched_at.elapsed() < CACHE_TTL {
-                    return Ok(data.clone());
-                }
+            cache.get(product).cloned()
+        };
+        if let Some((data, fetched_at)) = &cached {
+            if fetched_at.elapsed() < CACHE_TTL {
+                return Ok((data.clone(), false));
             }
         }
 
         // Fetch from API
-        let cycles = fetch_eol_from_api(product).await?;
+        let cycles = match fetch_eol_from_api(product).await {
+            Ok(cycles) => cycles,
+            Err(e) => match cached {
+                Some((data, _)) => {
+                    log::warn!("Using stale EOL data for {}: {}", product, e);
+                    return Ok((data, true));
+                }
+                None => return Err(e),
+            },
+        };
 
         // Update cache
         {
@@ -164,7 +186,7 @@ pub async fn get_eol_data(&self, env_type: &str) -> CogniaResult<Vec<EolCycleInf
             cache.insert(product.to_string(), (cycles.clone(), Instant::now()));
         }
 
-        Ok(cycles)
+        Ok((cycles, false))
     }
 
     /// Match a specific version to its EOL cycle.
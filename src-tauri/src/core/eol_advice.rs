@@ -0,0 +1,416 @@
+use crate::core::env_disk_usage::EnvProjectPin;
+use crate::core::eol::{match_version_to_cycle, EolCycleInfo};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Default window before an EOL date in which a version counts as approaching EOL.
+pub const DEFAULT_EOL_WARNING_DAYS: i64 = 180;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EolSupportStatus {
+    Supported,
+    Approaching,
+    Eol,
+    /// No EOL data for this version (unknown cycle or API unavailable)
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EolUpgradeTarget {
+    pub version: String,
+    pub cycle: String,
+    pub is_lts: bool,
+    /// `patch` (same cycle), `same_major`, `next_lts` or `next_supported`
+    pub reason: String,
+    pub eol: Option<String>,
+}
+
+/// EOL status and upgrade recommendation for one installed version.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EolVersionAdvice {
+    pub version: String,
+    pub is_current: bool,
+    pub cycle: Option<String>,
+    pub status: EolSupportStatus,
+    pub eol: Option<String>,
+    /// Negative once the EOL date has passed
+    pub days_until_eol: Option<i64>,
+    pub recommended: Option<EolUpgradeTarget>,
+    /// Known projects pinning this version
+    pub pinned_by: Vec<EnvProjectPin>,
+}
+
+/// Security posture of an environment: per-version EOL status plus a summary score.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvUpgradeAdvice {
+    pub env_type: String,
+    pub provider_id: String,
+    pub warning_days: i64,
+    /// 0-100, share of installed versions with known support weighted by status;
+    /// `None` when no version could be matched to EOL data
+    pub score: Option<u8>,
+    pub supported_count: usize,
+    pub approaching_count: usize,
+    pub eol_count: usize,
+    pub unknown_count: usize,
+    pub versions: Vec<EolVersionAdvice>,
+    pub scanned_projects: Vec<String>,
+    pub eol_available: bool,
+    /// EOL data came from an expired cache entry because the API was unreachable
+    pub eol_stale: bool,
+    pub eol_error: Option<String>,
+    pub checked_at: String,
+}
+
+/// Inputs for [`build_upgrade_advice`], gathered by the caller.
+pub struct UpgradeAdviceInput<'a> {
+    pub env_type: &'a str,
+    pub provider_id: &'a str,
+    /// `(version, is_current)`
+    pub installed: &'a [(String, bool)],
+    /// Versions the provider can install, any order
+    pub available: &'a [String],
+    /// `None` when EOL data is unavailable
+    pub cycles: Option<&'a [EolCycleInfo]>,
+    pub pins: &'a [EnvProjectPin],
+    pub warning_days: i64,
+    pub today: NaiveDate,
+}
+
+fn version_key(version: &str) -> Vec<u64> {
+    version
+        .trim()
+        .trim_start_matches('v')
+        .split(['.', '-', '+'])
+        .map_while(|part| part.parse::<u64>().ok())
+        .collect()
+}
+
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    version_key(a).cmp(&version_key(b))
+}
+
+fn version_in_cycle(version: &str, cycle: &str) -> bool {
+    let key = version_key(version);
+    let cycle_key = version_key(cycle);
+    !cycle_key.is_empty() && key.len() >= cycle_key.len() && key.starts_with(&cycle_key)
+}
+
+fn days_until(date: Option<&str>, today: NaiveDate) -> Option<i64> {
+    let date = NaiveDate::parse_from_str(date?, "%Y-%m-%d").ok()?;
+    Some(date.signed_duration_since(today).num_days())
+}
+
+fn cycle_status(cycle: &EolCycleInfo, warning_days: i64, today: NaiveDate) -> EolSupportStatus {
+    match days_until(cycle.eol.as_deref(), today) {
+        Some(days) if days < 0 => EolSupportStatus::Eol,
+        Some(days) if days <= warning_days => EolSupportStatus::Approaching,
+        _ if cycle.is_eol => EolSupportStatus::Eol,
+        _ => EolSupportStatus::Supported,
+    }
+}
+
+/// Newest installable version in `cycle`, falling back to the cycle's `latest`.
+fn latest_in_cycle(cycle: &EolCycleInfo, available: &[String]) -> Option<String> {
+    available
+        .iter()
+        .filter(|v| version_in_cycle(v, &cycle.cycle))
+        .max_by(|a, b| compare_versions(a, b))
+        .map(|v| v.trim_start_matches('v').to_string())
+        .or_else(|| cycle.latest.clone())
+}
+
+/// Closest supported upgrade: a newer patch in the same cycle while it is
+/// supported, otherwise the nearest newer cycle with the same major, then the
+/// nearest newer LTS, then any nearer supported cycle.
+fn recommend_target(
+    version: &str,
+    cycle: Option<&EolCycleInfo>,
+    status: EolSupportStatus,
+    input: &UpgradeAdviceInput<'_>,
+) -> Option<EolUpgradeTarget> {
+    let cycles = input.cycles?;
+    let target = |cycle: &EolCycleInfo, reason: &str| {
+        latest_in_cycle(cycle, input.available).map(|latest| EolUpgradeTarget {
+            version: latest,
+            cycle: cycle.cycle.clone(),
+            is_lts: cycle.lts.is_some(),
+            reason: reason.to_string(),
+            eol: cycle.eol.clone(),
+        })
+    };
+
+    if let (Some(cycle), EolSupportStatus::Supported) = (cycle, status) {
+        return target(cycle, "patch")
+            .filter(|t| compare_versions(&t.version, version) == Ordering::Greater);
+    }
+
+    let current_cycle_key = cycle.map(|c| version_key(&c.cycle));
+    let major = version_key(version).first().copied();
+    let mut newer: Vec<&EolCycleInfo> = cycles
+        .iter()
+        .filter(|c| cycle_status(c, input.warning_days, input.today) == EolSupportStatus::Supported)
+        .filter(|c| match &current_cycle_key {
+            Some(key) => version_key(&c.cycle) > *key,
+            None => compare_versions(&c.cycle, version) == Ordering::Greater,
+        })
+        .collect();
+    newer.sort_by(|a, b| compare_versions(&a.cycle, &b.cycle));
+
+    newer
+        .iter()
+        .find(|c| {
+            version_key(&c.cycle).len() > 1 && version_key(&c.cycle).first().copied() == major
+        })
+        .copied()
+        .and_then(|c| target(c, "same_major"))
+        .or_else(|| {
+            newer
+                .iter()
+                .find(|c| c.lts.is_some())
+                .copied()
+                .and_then(|c| target(c, "next_lts"))
+        })
+        .or_else(|| {
+            newer
+                .first()
+                .copied()
+                .and_then(|c| target(c, "next_supported"))
+        })
+}
+
+fn pin_matches(pin: &EnvProjectPin, version: &str) -> bool {
+    let pinned = pin.version.trim().trim_start_matches('v');
+    if pinned.is_empty() || version_key(pinned).is_empty() {
+        return false;
+    }
+    let key = version_key(version);
+    let pinned_key = version_key(pinned);
+    // Only plain numeric pins ("20", "3.12", "20.11.0") are matched
+    pinned_key.len() == pinned.split('.').count() && key.starts_with(&pinned_key)
+}
+
+/// Combine installed versions, available versions, EOL cycles and project pins
+/// into per-version advice and a summary score.
+pub fn build_upgrade_advice(input: &UpgradeAdviceInput<'_>) -> EnvUpgradeAdvice {
+    let mut versions: Vec<EolVersionAdvice> = input
+        .installed
+        .iter()
+        .map(|(version, is_current)| {
+            let cycle = input
+                .cycles
+                .and_then(|cycles| match_version_to_cycle(version, cycles));
+            let status = cycle
+                .as_ref()
+                .map(|c| cycle_status(c, input.warning_days, input.today))
+                .unwrap_or(EolSupportStatus::Unknown);
+            let recommended = recommend_target(version, cycle.as_ref(), status, input);
+            EolVersionAdvice {
+                version: version.clone(),
+                is_current: *is_current,
+                days_until_eol: cycle
+                    .as_ref()
+                    .and_then(|c| days_until(c.eol.as_deref(), input.today)),
+                eol: cycle.as_ref().and_then(|c| c.eol.clone()),
+                cycle: cycle.map(|c| c.cycle),
+                status,
+                recommended,
+                pinned_by: input
+                    .pins
+                    .iter()
+                    .filter(|pin| pin.env_type == input.env_type && pin_matches(pin, version))
+                    .cloned()
+                    .collect(),
+            }
+        })
+        .collect();
+    versions.sort_by(|a, b| compare_versions(&b.version, &a.version));
+
+    let count = |status: EolSupportStatus| versions.iter().filter(|v| v.status == status).count();
+    let supported_count = count(EolSupportStatus::Supported);
+    let approaching_count = count(EolSupportStatus::Approaching);
+    let eol_count = count(EolSupportStatus::Eol);
+    let known = supported_count + approaching_count + eol_count;
+    let score = (known > 0).then(|| {
+        ((supported_count as f64 + approaching_count as f64 * 0.5) / known as f64 * 100.0).round()
+            as u8
+    });
+
+    EnvUpgradeAdvice {
+        env_type: input.env_type.to_string(),
+        provider_id: input.provider_id.to_string(),
+        warning_days: input.warning_days,
+        score,
+        supported_count,
+        approaching_count,
+        eol_count,
+        unknown_count: versions.len() - known,
+        versions,
+        scanned_projects: Vec::new(),
+        eol_available: input.cycles.is_some(),
+        eol_stale: false,
+        eol_error: None,
+        checked_at: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cycle(name: &str, eol: &str, latest: &str, lts: bool) -> EolCycleInfo {
+        EolCycleInfo {
+            cycle: name.into(),
+            release_date: None,
+            eol: Some(eol.into()),
+            latest: Some(latest.into()),
+            lts: lts.then(|| "2020-01-01".to_string()),
+            support: None,
+            is_eol: false,
+            eol_approaching: false,
+        }
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()
+    }
+
+    fn node_cycles() -> Vec<EolCycleInfo> {
+        vec![
+            cycle("24", "2028-04-30", "24.3.0", true),
+            cycle("23", "2025-06-01", "23.11.1", false),
+            cycle("22", "2027-04-30", "22.12.0", true),
+            cycle("20", "2026-04-30", "20.19.0", true),
+            cycle("18", "2025-04-30", "18.20.8", true),
+        ]
+    }
+
+    fn pin(version: &str, dir: &str) -> EnvProjectPin {
+        EnvProjectPin {
+            env_type: "node".into(),
+            version: version.into(),
+            project_dir: dir.into(),
+            source: ".nvmrc".into(),
+        }
+    }
+
+    #[test]
+    fn test_node_advice_statuses_and_targets() {
+        let cycles = node_cycles();
+        let installed = vec![
+            ("18.19.0".to_string(), false),
+            ("20.11.0".to_string(), true),
+            ("22.1.0".to_string(), false),
+        ];
+        let available = vec![
+            "22.12.0".to_string(),
+            "v22.11.0".to_string(),
+            "24.3.0".to_string(),
+        ];
+        let pins = vec![pin("18", "/work/legacy"), pin("lts/*", "/work/app")];
+        let advice = build_upgrade_advice(&UpgradeAdviceInput {
+            env_type: "node",
+            provider_id: "fnm",
+            installed: &installed,
+            available: &available,
+            cycles: Some(&cycles),
+            pins: &pins,
+            warning_days: DEFAULT_EOL_WARNING_DAYS,
+            today: today(),
+        });
+
+        let by_version = |v: &str| advice.versions.iter().find(|a| a.version == v).unwrap();
+
+        let v22 = by_version("22.1.0");
+        assert_eq!(v22.status, EolSupportStatus::Supported);
+        let patch = v22.recommended.as_ref().unwrap();
+        assert_eq!(
+            (patch.version.as_str(), patch.reason.as_str()),
+            ("22.12.0", "patch")
+        );
+
+        // 20 reaches EOL within the warning window: move to the next LTS
+        let v20 = by_version("20.11.0");
+        assert_eq!(v20.status, EolSupportStatus::Approaching);
+        assert_eq!(v20.days_until_eol, Some(119));
+        let next = v20.recommended.as_ref().unwrap();
+        assert_eq!(
+            (next.cycle.as_str(), next.reason.as_str()),
+            ("22", "next_lts")
+        );
+
+        let v18 = by_version("18.19.0");
+        assert_eq!(v18.status, EolSupportStatus::Eol);
+        assert_eq!(v18.pinned_by.len(), 1);
+        assert_eq!(v18.pinned_by[0].project_dir, "/work/legacy");
+
+        assert_eq!(
+            (
+                advice.supported_count,
+                advice.approaching_count,
+                advice.eol_count
+            ),
+            (1, 1, 1)
+        );
+        assert_eq!(advice.score, Some(50));
+        assert_eq!(advice.versions[0].version, "22.1.0");
+    }
+
+    #[test]
+    fn test_python_prefers_same_major() {
+        let cycles = vec![
+            cycle("3.13", "2029-10-31", "3.13.1", false),
+            cycle("3.12", "2028-10-31", "3.12.8", false),
+            cycle("3.8", "2024-10-07", "3.8.20", false),
+        ];
+        let installed = vec![("3.8.10".to_string(), true)];
+        let advice = build_upgrade_advice(&UpgradeAdviceInput {
+            env_type: "python",
+            provider_id: "pyenv",
+            installed: &installed,
+            available: &[],
+            cycles: Some(&cycles),
+            pins: &[],
+            warning_days: 30,
+            today: today(),
+        });
+        let target = advice.versions[0].recommended.as_ref().unwrap();
+        assert_eq!(target.cycle, "3.12");
+        assert_eq!(target.version, "3.12.8");
+        assert_eq!(target.reason, "same_major");
+    }
+
+    #[test]
+    fn test_advice_without_eol_data() {
+        let installed = vec![("1.80.0".to_string(), true)];
+        let advice = build_upgrade_advice(&UpgradeAdviceInput {
+            env_type: "rust",
+            provider_id: "rustup",
+            installed: &installed,
+            available: &[],
+            cycles: None,
+            pins: &[],
+            warning_days: DEFAULT_EOL_WARNING_DAYS,
+            today: today(),
+        });
+        assert!(!advice.eol_available);
+        assert_eq!(advice.score, None);
+        assert_eq!(advice.unknown_count, 1);
+        assert_eq!(advice.versions[0].status, EolSupportStatus::Unknown);
+        assert!(advice.versions[0].recommended.is_none());
+    }
+
+    #[test]
+    fn test_pin_matches() {
+        assert!(pin_matches(&pin("20", "/p"), "20.11.0"));
+        assert!(pin_matches(&pin("v20.11.0", "/p"), "20.11.0"));
+        assert!(!pin_matches(&pin("20.12", "/p"), "20.11.0"));
+        assert!(!pin_matches(&pin(">=20", "/p"), "20.11.0"));
+        assert!(!pin_matches(&pin("lts/iron", "/p"), "20.11.0"));
+    }
+}
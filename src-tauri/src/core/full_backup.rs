@@ -0,0 +1,1392 @@
+//! Full backups of the Cognia state directory.
+//!
+//! The content backups in [`super::backup`] serialize selected data through
+//! each subsystem. A full backup instead archives the state files as they are
+//! on disk, grouped into [`StateComponent`]s, into a single zip whose manifest
+//! records every file checksum and each component's schema version.
+//!
+//! A restore never partially overwrites state: the selected components are
+//! extracted next to their targets, verified and migrated there, and only
+//! then swapped into place. If any swap fails, the components already
+//! swapped are rolled back.
+
+use super::backup::{
+    issue, operation_failure_reason, try_acquire_backup_mutation_gate, BackupOperationIssue,
+    BackupOperationStatus,
+};
+use crate::config::{Settings, CURRENT_SCHEMA_VERSION, UNVERSIONED_SCHEMA_VERSION};
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::fs;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Layout version of the archive itself
+pub const FULL_BACKUP_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_ENTRY: &str = "manifest.json";
+const COMPONENTS_PREFIX: &str = "components";
+/// Secret material in the config dir never leaves this machine
+const EXCLUDED_CONFIG_PREFIX: &str = "secrets.";
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// A group of state files restored as a unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StateComponent {
+    Config,
+    TerminalProfiles,
+    CustomRules,
+    Workspaces,
+    State,
+    Shims,
+    Plugins,
+    PluginData,
+    DownloadCache,
+}
+
+impl StateComponent {
+    pub const ALL: [Self; 9] = [
+        Self::Config,
+        Self::TerminalProfiles,
+        Self::CustomRules,
+        Self::Workspaces,
+        Self::State,
+        Self::Shims,
+        Self::Plugins,
+        Self::PluginData,
+        Self::DownloadCache,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Config => "config",
+            Self::TerminalProfiles => "terminal_profiles",
+            Self::CustomRules => "custom_rules",
+            Self::Workspaces => "workspaces",
+            Self::State => "state",
+            Self::Shims => "shims",
+            Self::Plugins => "plugins",
+            Self::PluginData => "plugin_data",
+            Self::DownloadCache => "download_cache",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|component| component.as_str() == s)
+    }
+
+    /// Newest schema this release can restore. Components other than the
+    /// config file are copied verbatim, so their version only changes with
+    /// their on-disk layout.
+    pub fn schema_version(&self) -> u32 {
+        match self {
+            Self::Config => CURRENT_SCHEMA_VERSION,
+            _ => 1,
+        }
+    }
+
+    /// Whether the component is a single file rather than a directory
+    fn is_file(&self) -> bool {
+        matches!(self, Self::CustomRules | Self::Workspaces)
+    }
+
+    /// Components that running subsystems keep in memory; they only take
+    /// effect after a restart. The rest are reloaded in place.
+    pub fn requires_restart(&self) -> bool {
+        matches!(
+            self,
+            Self::State | Self::Plugins | Self::PluginData | Self::DownloadCache
+        )
+    }
+}
+
+/// Where each component lives on this machine
+#[derive(Debug, Clone)]
+pub struct StateLocations {
+    pub root_dir: PathBuf,
+    pub config_dir: PathBuf,
+    /// App config dir holding custom detection rules and workspaces
+    pub app_config_dir: PathBuf,
+    pub cache_dir: PathBuf,
+}
+
+impl StateLocations {
+    pub fn new(settings: &Settings, app_config_dir: PathBuf) -> Self {
+        let root_dir = settings.get_root_dir();
+        Self {
+            config_dir: fs::get_config_dir().unwrap_or_else(|| root_dir.join("config")),
+            cache_dir: settings.get_cache_dir(),
+            root_dir,
+            app_config_dir,
+        }
+    }
+
+    pub fn path(&self, component: StateComponent) -> PathBuf {
+        match component {
+            StateComponent::Config => self.config_dir.clone(),
+            StateComponent::TerminalProfiles => self.root_dir.join("terminal"),
+            StateComponent::CustomRules => self.app_config_dir.join("custom_detection_rules.json"),
+            StateComponent::Workspaces => self.app_config_dir.join("workspaces.json"),
+            StateComponent::State => self.root_dir.join("state"),
+            StateComponent::Shims => self.root_dir.join("shims"),
+            StateComponent::Plugins => self.root_dir.join("plugins"),
+            StateComponent::PluginData => self.root_dir.join("plugin-data"),
+            StateComponent::DownloadCache => self.cache_dir.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FullBackupOptions {
+    /// Archive path, defaults to `<root>/backups/cognia-full-<timestamp>.zip`
+    pub dest_path: Option<String>,
+    pub include_download_cache: bool,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FullBackupManifest {
+    pub format_version: u32,
+    pub app_version: String,
+    pub created_at: String,
+    pub platform: String,
+    pub hostname: String,
+    #[serde(default)]
+    pub note: Option<String>,
+    pub components: Vec<FullBackupComponentEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FullBackupComponentEntry {
+    pub component: StateComponent,
+    pub schema_version: u32,
+    pub file_count: u64,
+    pub total_size: u64,
+    /// Path relative to the component -> sha256
+    pub files: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FullBackupPhase {
+    Scanning,
+    Archiving,
+    Staging,
+    Migrating,
+    Swapping,
+    Done,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FullBackupProgress {
+    pub phase: FullBackupPhase,
+    pub component: Option<StateComponent>,
+    pub files_done: u64,
+    pub total_files: u64,
+    pub bytes_done: u64,
+    pub total_bytes: u64,
+}
+
+pub type FullBackupProgressCallback = Arc<dyn Fn(FullBackupProgress) + Send + Sync>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FullBackupResult {
+    pub success: bool,
+    pub status: BackupOperationStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason_code: Option<String>,
+    #[serde(default)]
+    pub issues: Vec<BackupOperationIssue>,
+    pub path: String,
+    pub manifest: Option<FullBackupManifest>,
+    pub size: u64,
+    pub duration_ms: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FullBackupComponentStatus {
+    pub component: StateComponent,
+    pub schema_version: u32,
+    pub supported_schema_version: u32,
+    pub file_count: u64,
+    pub total_size: u64,
+    /// Written by a newer release; cannot be restored
+    pub compatible: bool,
+    /// Older schema, upgraded while restoring
+    pub needs_migration: bool,
+    pub requires_restart: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FullBackupInspection {
+    pub valid: bool,
+    pub path: String,
+    pub size: u64,
+    #[serde(default)]
+    pub issues: Vec<BackupOperationIssue>,
+    pub manifest: Option<FullBackupManifest>,
+    pub components: Vec<FullBackupComponentStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FullRestoreResult {
+    pub success: bool,
+    pub status: BackupOperationStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason_code: Option<String>,
+    #[serde(default)]
+    pub issues: Vec<BackupOperationIssue>,
+    pub restored: Vec<StateComponent>,
+    /// Schema version the restored config file was upgraded from
+    pub config_migrated_from: Option<u32>,
+    pub restart_required: bool,
+    pub duration_ms: u64,
+    pub error: Option<String>,
+}
+
+impl FullRestoreResult {
+    fn failed(code: &str, message: impl Into<String>, content_type: Option<String>) -> Self {
+        let message = message.into();
+        Self {
+            success: false,
+            status: BackupOperationStatus::Failed,
+            reason_code: Some(code.to_string()),
+            issues: vec![issue(code, message.clone(), content_type)],
+            restored: vec![],
+            config_migrated_from: None,
+            restart_required: false,
+            duration_ms: 0,
+            error: Some(message),
+        }
+    }
+}
+
+// ============================================================================
+// Progress
+// ============================================================================
+
+/// Forwards progress at most once per [`PROGRESS_INTERVAL`], except for
+/// phase and component changes which are always reported
+struct ProgressReporter {
+    callback: Option<FullBackupProgressCallback>,
+    last: Option<Instant>,
+    current: FullBackupProgress,
+}
+
+impl ProgressReporter {
+    fn new(callback: Option<FullBackupProgressCallback>) -> Self {
+        Self {
+            callback,
+            last: None,
+            current: FullBackupProgress {
+                phase: FullBackupPhase::Scanning,
+                component: None,
+                files_done: 0,
+                total_files: 0,
+                bytes_done: 0,
+                total_bytes: 0,
+            },
+        }
+    }
+
+    fn set_totals(&mut self, total_files: u64, total_bytes: u64) {
+        self.current.total_files = total_files;
+        self.current.total_bytes = total_bytes;
+    }
+
+    fn enter(&mut self, phase: FullBackupPhase, component: Option<StateComponent>) {
+        self.current.phase = phase;
+        self.current.component = component;
+        self.emit(true);
+    }
+
+    fn file_done(&mut self, bytes: u64) {
+        self.current.files_done += 1;
+        self.current.bytes_done += bytes;
+        self.emit(false);
+    }
+
+    fn emit(&mut self, force: bool) {
+        let Some(callback) = &self.callback else {
+            return;
+        };
+        if force || !self.last.is_some_and(|t| t.elapsed() < PROGRESS_INTERVAL) {
+            self.last = Some(Instant::now());
+            callback(self.current.clone());
+        }
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+struct SourceFile {
+    path: PathBuf,
+    relative: String,
+    size: u64,
+}
+
+fn archive_entry_name(component: StateComponent, relative: &str) -> String {
+    format!("{}/{}/{}", COMPONENTS_PREFIX, component.as_str(), relative)
+}
+
+fn is_excluded(component: StateComponent, relative: &str) -> bool {
+    component == StateComponent::Config
+        && !relative.contains('/')
+        && relative.starts_with(EXCLUDED_CONFIG_PREFIX)
+}
+
+/// A relative path from the manifest that stays inside its component
+fn safe_relative_path(relative: &str) -> Option<PathBuf> {
+    let path = PathBuf::from(relative);
+    let safe = !relative.is_empty()
+        && path
+            .components()
+            .all(|part| matches!(part, Component::Normal(_)));
+    safe.then_some(path)
+}
+
+/// Sibling of `target` used while restoring, e.g. `.plugins.restore-staging-<ts>`
+fn sibling_path(target: &Path, purpose: &str, stamp: &str) -> PathBuf {
+    let name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "state".into());
+    target.with_file_name(format!(".{}.{}-{}", name, purpose, stamp))
+}
+
+fn remove_path(path: &Path) {
+    let result = match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.is_dir() => std::fs::remove_dir_all(path),
+        Ok(_) => std::fs::remove_file(path),
+        Err(_) => return,
+    };
+    if let Err(e) = result {
+        log::warn!("Failed to remove {}: {}", path.display(), e);
+    }
+}
+
+/// Copy `reader` into `writer`, returning the sha256 and byte count
+fn copy_hashed(reader: &mut impl Read, writer: &mut impl Write) -> std::io::Result<(String, u64)> {
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut total = 0u64;
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        writer.write_all(&buffer[..read])?;
+        total += read as u64;
+    }
+    Ok((hex::encode(hasher.finalize()), total))
+}
+
+fn scan_component(component: StateComponent, path: &Path) -> CogniaResult<Vec<SourceFile>> {
+    if component.is_file() {
+        return Ok(match std::fs::metadata(path) {
+            Ok(meta) if meta.is_file() => vec![SourceFile {
+                path: path.to_path_buf(),
+                relative: path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                size: meta.len(),
+            }],
+            _ => vec![],
+        });
+    }
+    if !path.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let mut files = Vec::new();
+    for entry in walkdir::WalkDir::new(path).sort_by_file_name() {
+        let entry = entry.map_err(|e| {
+            CogniaError::Internal(format!("Failed to scan {}: {}", path.display(), e))
+        })?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(path)
+            .unwrap_or(entry.path())
+            .to_string_lossy()
+            .replace('\\', "/");
+        if is_excluded(component, &relative) {
+            continue;
+        }
+        files.push(SourceFile {
+            size: entry.metadata().map(|meta| meta.len()).unwrap_or(0),
+            path: entry.path().to_path_buf(),
+            relative,
+        });
+    }
+    Ok(files)
+}
+
+/// `schema_version` of the config file on disk
+fn config_schema_version(config_dir: &Path) -> u32 {
+    let Ok(content) = std::fs::read_to_string(config_dir.join("config.toml")) else {
+        return CURRENT_SCHEMA_VERSION;
+    };
+    toml::from_str::<toml::Table>(&content)
+        .ok()
+        .and_then(|raw| raw.get("schema_version").and_then(|v| v.as_integer()))
+        .and_then(|version| u32::try_from(version).ok())
+        .unwrap_or(UNVERSIONED_SCHEMA_VERSION)
+}
+
+fn read_manifest<R: Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+) -> CogniaResult<FullBackupManifest> {
+    let mut entry = archive
+        .by_name(MANIFEST_ENTRY)
+        .map_err(|_| CogniaError::Internal("manifest.json not found in archive".into()))?;
+    let mut content = String::new();
+    entry
+        .read_to_string(&mut content)
+        .map_err(|e| CogniaError::Internal(format!("ZIP read error: {}", e)))?;
+    serde_json::from_str(&content)
+        .map_err(|e| CogniaError::Parse(format!("Invalid manifest.json: {}", e)))
+}
+
+fn open_archive(path: &Path) -> CogniaResult<zip::ZipArchive<std::fs::File>> {
+    let file = std::fs::File::open(path).map_err(CogniaError::Io)?;
+    zip::ZipArchive::new(file).map_err(|e| CogniaError::Internal(format!("Invalid ZIP: {}", e)))
+}
+
+fn default_archive_path(root_dir: &Path) -> PathBuf {
+    root_dir.join("backups").join(format!(
+        "cognia-full-{}.zip",
+        Utc::now().format("%Y%m%d-%H%M%S")
+    ))
+}
+
+fn component_status(entry: &FullBackupComponentEntry) -> FullBackupComponentStatus {
+    let supported = entry.component.schema_version();
+    FullBackupComponentStatus {
+        component: entry.component,
+        schema_version: entry.schema_version,
+        supported_schema_version: supported,
+        file_count: entry.file_count,
+        total_size: entry.total_size,
+        compatible: entry.schema_version <= supported,
+        needs_migration: entry.schema_version < supported,
+        requires_restart: entry.component.requires_restart(),
+    }
+}
+
+// ============================================================================
+// Create
+// ============================================================================
+
+/// Archive every state component into a single zip. The download cache is
+/// only included when requested.
+pub async fn create_full_backup(
+    locations: &StateLocations,
+    options: &FullBackupOptions,
+    on_progress: Option<FullBackupProgressCallback>,
+) -> CogniaResult<FullBackupResult> {
+    let start = Instant::now();
+    let dest = options
+        .dest_path
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_archive_path(&locations.root_dir));
+    if fs::exists(&dest).await {
+        return Err(CogniaError::Internal(format!(
+            "Backup destination already exists: {}",
+            dest.display()
+        )));
+    }
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+
+    let components: Vec<StateComponent> = StateComponent::ALL
+        .into_iter()
+        .filter(|c| options.include_download_cache || *c != StateComponent::DownloadCache)
+        .collect();
+    let manifest = FullBackupManifest {
+        format_version: FULL_BACKUP_FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: Utc::now().to_rfc3339(),
+        platform: std::env::consts::OS.to_string(),
+        hostname: sysinfo::System::host_name().unwrap_or_else(|| "unknown".to_string()),
+        note: options.note.clone(),
+        components: vec![],
+    };
+
+    let locations = locations.clone();
+    let archive_path = dest.clone();
+    let manifest = tokio::task::spawn_blocking(move || {
+        let mut reporter = ProgressReporter::new(on_progress);
+        write_archive(
+            &locations,
+            &components,
+            manifest,
+            &archive_path,
+            &mut reporter,
+        )
+    })
+    .await
+    .map_err(|e| CogniaError::Internal(format!("Backup task failed: {}", e)))??;
+
+    let size = fs::file_size(&dest).await?;
+    Ok(FullBackupResult {
+        success: true,
+        status: BackupOperationStatus::Success,
+        reason_code: None,
+        issues: vec![],
+        path: dest.display().to_string(),
+        manifest: Some(manifest),
+        size,
+        duration_ms: start.elapsed().as_millis() as u64,
+        error: None,
+    })
+}
+
+fn write_archive(
+    locations: &StateLocations,
+    components: &[StateComponent],
+    mut manifest: FullBackupManifest,
+    dest: &Path,
+    reporter: &mut ProgressReporter,
+) -> CogniaResult<FullBackupManifest> {
+    reporter.enter(FullBackupPhase::Scanning, None);
+    let mut sources = Vec::new();
+    for component in components {
+        let files = scan_component(*component, &locations.path(*component))?;
+        if !files.is_empty() {
+            sources.push((*component, files));
+        }
+    }
+    let total_files = sources.iter().map(|(_, files)| files.len() as u64).sum();
+    let total_bytes = sources
+        .iter()
+        .flat_map(|(_, files)| files.iter().map(|f| f.size))
+        .sum();
+    reporter.set_totals(total_files, total_bytes);
+
+    let temp_dest = dest.with_extension(format!("tmp-{}", Utc::now().timestamp_millis()));
+    let result = (|| -> CogniaResult<FullBackupManifest> {
+        let file = std::fs::File::create(&temp_dest).map_err(CogniaError::Io)?;
+        let mut zip_writer = zip::ZipWriter::new(file);
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .large_file(true);
+
+        for (component, files) in &sources {
+            reporter.enter(FullBackupPhase::Archiving, Some(*component));
+            let mut entry = FullBackupComponentEntry {
+                component: *component,
+                schema_version: match component {
+                    StateComponent::Config => config_schema_version(&locations.config_dir),
+                    other => other.schema_version(),
+                },
+                file_count: 0,
+                total_size: 0,
+                files: BTreeMap::new(),
+            };
+            for source in files {
+                let mut reader = std::fs::File::open(&source.path).map_err(CogniaError::Io)?;
+                zip_writer
+                    .start_file(archive_entry_name(*component, &source.relative), options)
+                    .map_err(|e| CogniaError::Internal(format!("ZIP write error: {}", e)))?;
+                let (checksum, size) =
+                    copy_hashed(&mut reader, &mut zip_writer).map_err(CogniaError::Io)?;
+                entry.files.insert(source.relative.clone(), checksum);
+                entry.file_count += 1;
+                entry.total_size += size;
+                reporter.file_done(size);
+            }
+            manifest.components.push(entry);
+        }
+
+        let manifest_json = serde_json::to_string_pretty(&manifest)
+            .map_err(|e| CogniaError::Internal(format!("Failed to serialize manifest: {}", e)))?;
+        zip_writer
+            .start_file(MANIFEST_ENTRY, options)
+            .map_err(|e| CogniaError::Internal(format!("ZIP write error: {}", e)))?;
+        zip_writer
+            .write_all(manifest_json.as_bytes())
+            .map_err(|e| CogniaError::Internal(format!("ZIP write error: {}", e)))?;
+        zip_writer
+            .finish()
+            .map_err(|e| CogniaError::Internal(format!("ZIP finish error: {}", e)))?;
+
+        std::fs::rename(&temp_dest, dest).map_err(|e| {
+            CogniaError::Internal(format!(
+                "Failed to finalize backup archive '{}': {}",
+                dest.display(),
+                e
+            ))
+        })?;
+        Ok(manifest)
+    })();
+
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_dest);
+    }
+    reporter.enter(FullBackupPhase::Done, None);
+    result
+}
+
+pub async fn create_full_backup_with_result(
+    locations: &StateLocations,
+    options: &FullBackupOptions,
+    on_progress: Option<FullBackupProgressCallback>,
+) -> FullBackupResult {
+    let failed = |code: &str, status: BackupOperationStatus, message: String| FullBackupResult {
+        success: false,
+        status,
+        reason_code: Some(code.to_string()),
+        issues: vec![issue(code, message.clone(), None)],
+        path: String::new(),
+        manifest: None,
+        size: 0,
+        duration_ms: 0,
+        error: Some(message),
+    };
+
+    let _gate = match try_acquire_backup_mutation_gate("full_create") {
+        Ok(guard) => guard,
+        Err(err) => {
+            return failed("operation_in_progress", BackupOperationStatus::Skipped, err);
+        }
+    };
+
+    match create_full_backup(locations, options, on_progress).await {
+        Ok(result) => result,
+        Err(err) => failed(
+            &operation_failure_reason("full_create", &err),
+            BackupOperationStatus::Failed,
+            err.to_string(),
+        ),
+    }
+}
+
+// ============================================================================
+// Inspect
+// ============================================================================
+
+/// Read the manifest of a full backup and check every listed file is present
+/// and every component can be restored by this release
+pub async fn inspect_full_backup(path: &Path) -> FullBackupInspection {
+    let archive_path = path.to_path_buf();
+    let size = fs::file_size(path).await.unwrap_or(0);
+    let inspected = match tokio::task::spawn_blocking(move || inspect_archive(&archive_path)).await
+    {
+        Ok(result) => result,
+        Err(e) => Err(CogniaError::Internal(format!("Inspect task failed: {}", e))),
+    };
+
+    match inspected {
+        Ok((manifest, issues)) => FullBackupInspection {
+            valid: issues.is_empty(),
+            path: path.display().to_string(),
+            size,
+            issues,
+            components: manifest.components.iter().map(component_status).collect(),
+            manifest: Some(manifest),
+        },
+        Err(err) => FullBackupInspection {
+            valid: false,
+            path: path.display().to_string(),
+            size,
+            issues: vec![issue(
+                &operation_failure_reason("inspect", &err),
+                err.to_string(),
+                None,
+            )],
+            manifest: None,
+            components: vec![],
+        },
+    }
+}
+
+fn inspect_archive(path: &Path) -> CogniaResult<(FullBackupManifest, Vec<BackupOperationIssue>)> {
+    let mut archive = open_archive(path)?;
+    let manifest = read_manifest(&mut archive)?;
+    let mut issues = Vec::new();
+
+    if manifest.format_version != FULL_BACKUP_FORMAT_VERSION {
+        issues.push(issue(
+            "unsupported_format_version",
+            format!(
+                "Unsupported full backup format version: {}",
+                manifest.format_version
+            ),
+            None,
+        ));
+    }
+
+    for entry in &manifest.components {
+        let content_type = Some(entry.component.as_str().to_string());
+        if entry.schema_version > entry.component.schema_version() {
+            issues.push(issue(
+                "component_schema_unsupported",
+                format!(
+                    "{} was written with schema v{}, this release supports up to v{}",
+                    entry.component.as_str(),
+                    entry.schema_version,
+                    entry.component.schema_version()
+                ),
+                content_type.clone(),
+            ));
+        }
+        for relative in entry.files.keys() {
+            let name = archive_entry_name(entry.component, relative);
+            if safe_relative_path(relative).is_none() || archive.by_name(&name).is_err() {
+                issues.push(issue(
+                    "archive_missing_file",
+                    format!("Missing or unsafe archive entry: {}", name),
+                    content_type.clone(),
+                ));
+            }
+        }
+    }
+
+    Ok((manifest, issues))
+}
+
+// ============================================================================
+// Restore
+// ============================================================================
+
+/// Restore `components` (all components in the archive when empty) from a
+/// full backup.
+///
+/// Every component is extracted into a staging path next to its target and
+/// verified against the manifest checksums; a config file from an older
+/// release is upgraded by the settings migration pipeline. Only when all
+/// components are staged are they swapped into place.
+pub async fn restore_full_backup(
+    archive_path: &Path,
+    components: &[StateComponent],
+    locations: &StateLocations,
+    on_progress: Option<FullBackupProgressCallback>,
+) -> CogniaResult<FullRestoreResult> {
+    let start = Instant::now();
+    let archive_path = archive_path.to_path_buf();
+    let components = components.to_vec();
+    let locations = locations.clone();
+
+    let mut result = tokio::task::spawn_blocking(move || {
+        let mut reporter = ProgressReporter::new(on_progress);
+        let result = restore_archive(&archive_path, &components, &locations, &mut reporter);
+        reporter.enter(FullBackupPhase::Done, None);
+        result
+    })
+    .await
+    .map_err(|e| CogniaError::Internal(format!("Restore task failed: {}", e)))??;
+
+    result.duration_ms = start.elapsed().as_millis() as u64;
+    Ok(result)
+}
+
+fn restore_archive(
+    archive_path: &Path,
+    requested: &[StateComponent],
+    locations: &StateLocations,
+    reporter: &mut ProgressReporter,
+) -> CogniaResult<FullRestoreResult> {
+    let mut archive = open_archive(archive_path)?;
+    let manifest = read_manifest(&mut archive)?;
+    if manifest.format_version != FULL_BACKUP_FORMAT_VERSION {
+        return Ok(FullRestoreResult::failed(
+            "unsupported_format_version",
+            format!(
+                "Unsupported full backup format version: {}",
+                manifest.format_version
+            ),
+            None,
+        ));
+    }
+
+    // Resolve and validate the selection before touching anything
+    let mut selected: Vec<&FullBackupComponentEntry> = Vec::new();
+    if requested.is_empty() {
+        selected.extend(manifest.components.iter());
+    } else {
+        for component in requested {
+            match manifest
+                .components
+                .iter()
+                .find(|entry| entry.component == *component)
+            {
+                Some(entry) => selected.push(entry),
+                None => {
+                    return Ok(FullRestoreResult::failed(
+                        "component_not_in_backup",
+                        format!("Backup does not contain {}", component.as_str()),
+                        Some(component.as_str().to_string()),
+                    ));
+                }
+            }
+        }
+    }
+    for entry in &selected {
+        let status = component_status(entry);
+        if !status.compatible {
+            return Ok(FullRestoreResult::failed(
+                "component_schema_unsupported",
+                format!(
+                    "{} was written with schema v{}, this release supports up to v{}",
+                    entry.component.as_str(),
+                    status.schema_version,
+                    status.supported_schema_version
+                ),
+                Some(entry.component.as_str().to_string()),
+            ));
+        }
+    }
+    reporter.set_totals(
+        selected.iter().map(|entry| entry.file_count).sum(),
+        selected.iter().map(|entry| entry.total_size).sum(),
+    );
+
+    let stamp = Utc::now().format("%Y%m%d%H%M%S%3f").to_string();
+    let mut staged: Vec<(StateComponent, PathBuf)> = Vec::new();
+    let mut config_migrated_from = None;
+
+    let staging_result = (|| -> CogniaResult<Option<FullRestoreResult>> {
+        for entry in &selected {
+            let component = entry.component;
+            let target = locations.path(component);
+            let staging = sibling_path(&target, "restore-staging", &stamp);
+            staged.push((component, staging.clone()));
+            if let Some(failure) = stage_component(&mut archive, entry, &staging, reporter)? {
+                return Ok(Some(failure));
+            }
+
+            if component == StateComponent::Config {
+                reporter.enter(FullBackupPhase::Migrating, Some(component));
+                match migrate_staged_config(&staging) {
+                    Ok(from) => config_migrated_from = from,
+                    Err(err) => {
+                        return Ok(Some(FullRestoreResult::failed(
+                            "config_invalid",
+                            err.to_string(),
+                            Some(component.as_str().to_string()),
+                        )));
+                    }
+                }
+                preserve_excluded_files(&target, &staging)?;
+            }
+        }
+        Ok(None)
+    })();
+
+    match staging_result {
+        Ok(None) => {}
+        Ok(Some(failure)) => {
+            for (_, staging) in &staged {
+                remove_path(staging);
+            }
+            return Ok(failure);
+        }
+        Err(err) => {
+            for (_, staging) in &staged {
+                remove_path(staging);
+            }
+            return Err(err);
+        }
+    }
+
+    // Swap staged components in, rolling everything back on the first failure
+    let mut swapped: Vec<(PathBuf, Option<PathBuf>)> = Vec::new();
+    for (component, staging) in &staged {
+        reporter.enter(FullBackupPhase::Swapping, Some(*component));
+        let target = locations.path(*component);
+        let previous = sibling_path(&target, "restore-previous", &stamp);
+        let had_previous = std::fs::symlink_metadata(&target).is_ok();
+
+        let swap = (|| -> std::io::Result<()> {
+            if had_previous {
+                std::fs::rename(&target, &previous)?;
+            }
+            if let Err(e) = std::fs::rename(staging, &target) {
+                if had_previous {
+                    let _ = std::fs::rename(&previous, &target);
+                }
+                return Err(e);
+            }
+            Ok(())
+        })();
+
+        if let Err(e) = swap {
+            for (target, previous) in swapped.iter().rev() {
+                remove_path(target);
+                if let Some(previous) = previous {
+                    if let Err(e) = std::fs::rename(previous, target) {
+                        log::error!(
+                            "Failed to roll back {} from {}: {}",
+                            target.display(),
+                            previous.display(),
+                            e
+                        );
+                    }
+                }
+            }
+            for (_, staging) in &staged {
+                remove_path(staging);
+            }
+            return Ok(FullRestoreResult::failed(
+                "restore_swap_failed",
+                format!(
+                    "Failed to replace {}: {}; no state was changed",
+                    target.display(),
+                    e
+                ),
+                Some(component.as_str().to_string()),
+            ));
+        }
+        swapped.push((target, had_previous.then_some(previous)));
+    }
+
+    for previous in swapped.iter().filter_map(|(_, previous)| previous.as_ref()) {
+        remove_path(previous);
+    }
+
+    let restored: Vec<StateComponent> = staged.iter().map(|(component, _)| *component).collect();
+    Ok(FullRestoreResult {
+        success: true,
+        status: BackupOperationStatus::Success,
+        reason_code: None,
+        issues: vec![],
+        restart_required: restored.iter().any(|c| c.requires_restart()),
+        restored,
+        config_migrated_from,
+        duration_ms: 0,
+        error: None,
+    })
+}
+
+/// Extract one component into `staging`, verifying every checksum
+fn stage_component<R: Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    entry: &FullBackupComponentEntry,
+    staging: &Path,
+    reporter: &mut ProgressReporter,
+) -> CogniaResult<Option<FullRestoreResult>> {
+    let component = entry.component;
+    let content_type = Some(component.as_str().to_string());
+    reporter.enter(FullBackupPhase::Staging, Some(component));
+
+    if !component.is_file() {
+        std::fs::create_dir_all(staging).map_err(CogniaError::Io)?;
+    }
+
+    for (relative, expected) in &entry.files {
+        let Some(relative_path) = safe_relative_path(relative) else {
+            return Ok(Some(FullRestoreResult::failed(
+                "archive_unsafe_path",
+                format!("Unsafe path in backup manifest: {}", relative),
+                content_type,
+            )));
+        };
+        let name = archive_entry_name(component, relative);
+        let mut reader = match archive.by_name(&name) {
+            Ok(reader) => reader,
+            Err(_) => {
+                return Ok(Some(FullRestoreResult::failed(
+                    "archive_missing_file",
+                    format!("Missing archive entry: {}", name),
+                    content_type,
+                )));
+            }
+        };
+
+        let dest = if component.is_file() {
+            staging.to_path_buf()
+        } else {
+            staging.join(relative_path)
+        };
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent).map_err(CogniaError::Io)?;
+        }
+        let mut writer = std::fs::File::create(&dest).map_err(CogniaError::Io)?;
+        let (checksum, size) = copy_hashed(&mut reader, &mut writer).map_err(CogniaError::Io)?;
+        if checksum != *expected {
+            return Ok(Some(FullRestoreResult::failed(
+                "restore_checksum_mismatch",
+                format!("Checksum mismatch in backup file: {}", name),
+                content_type,
+            )));
+        }
+        reporter.file_done(size);
+    }
+
+    Ok(None)
+}
+
+/// Run a staged `config.toml` through the settings migration pipeline.
+/// Returns the version it was upgraded from, if it was.
+fn migrate_staged_config(staging: &Path) -> CogniaResult<Option<u32>> {
+    let path = staging.join("config.toml");
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&path).map_err(CogniaError::Io)?;
+    let (settings, report) = Settings::parse_versioned(&content)?;
+    if !report.migrated() {
+        return Ok(None);
+    }
+
+    let migrated = settings
+        .to_toml_string()
+        .map_err(|e| CogniaError::Internal(format!("Config serialize failed: {}", e)))?;
+    std::fs::write(&path, migrated).map_err(CogniaError::Io)?;
+    Ok(Some(report.from_version))
+}
+
+/// Carry over the files a full backup never contains, so swapping the
+/// config dir keeps this machine's secrets
+fn preserve_excluded_files(target: &Path, staging: &Path) -> CogniaResult<()> {
+    let Ok(entries) = std::fs::read_dir(target) else {
+        return Ok(());
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if entry.path().is_file() && is_excluded(StateComponent::Config, &name) {
+            std::fs::copy(entry.path(), staging.join(&name)).map_err(CogniaError::Io)?;
+        }
+    }
+    Ok(())
+}
+
+pub async fn restore_full_backup_with_result(
+    archive_path: &Path,
+    components: &[StateComponent],
+    locations: &StateLocations,
+    on_progress: Option<FullBackupProgressCallback>,
+) -> FullRestoreResult {
+    let _gate = match try_acquire_backup_mutation_gate("full_restore") {
+        Ok(guard) => guard,
+        Err(err) => {
+            let mut result = FullRestoreResult::failed("operation_in_progress", err, None);
+            result.status = BackupOperationStatus::Skipped;
+            return result;
+        }
+    };
+
+    match restore_full_backup(archive_path, components, locations, on_progress).await {
+        Ok(result) => result,
+        Err(err) => FullRestoreResult::failed(
+            &operation_failure_reason("full_restore", &err),
+            err.to_string(),
+            None,
+        ),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locations(root: &Path) -> StateLocations {
+        StateLocations {
+            root_dir: root.join("cognia"),
+            config_dir: root.join("cognia").join("config"),
+            app_config_dir: root.join("app-config"),
+            cache_dir: root.join("cognia").join("cache"),
+        }
+    }
+
+    fn write(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn seed(locations: &StateLocations) {
+        write(
+            &locations.config_dir.join("config.toml"),
+            &format!("schema_version = {}\n", CURRENT_SCHEMA_VERSION),
+        );
+        write(&locations.config_dir.join("secrets.key"), "local-key");
+        write(
+            &locations
+                .path(StateComponent::TerminalProfiles)
+                .join("profiles.json"),
+            "[]",
+        );
+        write(&locations.path(StateComponent::CustomRules), "[]");
+        write(
+            &locations
+                .path(StateComponent::PluginData)
+                .join("sample")
+                .join("data")
+                .join("state.json"),
+            "{\"count\":1}",
+        );
+        write(&locations.cache_dir.join("cache.db"), "db");
+    }
+
+    async fn create(locations: &StateLocations, include_download_cache: bool) -> FullBackupResult {
+        let options = FullBackupOptions {
+            include_download_cache,
+            ..Default::default()
+        };
+        create_full_backup(locations, &options, None).await.unwrap()
+    }
+
+    #[test]
+    fn test_state_component_round_trip() {
+        for component in StateComponent::ALL {
+            assert_eq!(
+                StateComponent::from_str(component.as_str()),
+                Some(component)
+            );
+        }
+        assert_eq!(StateComponent::from_str("cache_database"), None);
+    }
+
+    #[test]
+    fn test_safe_relative_path_rejects_escapes() {
+        assert!(safe_relative_path("sample/data/state.json").is_some());
+        assert!(safe_relative_path("../outside").is_none());
+        assert!(safe_relative_path("/etc/passwd").is_none());
+        assert!(safe_relative_path("").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_create_excludes_download_cache_and_secrets_by_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let locations = locations(dir.path());
+        seed(&locations);
+
+        let result = create(&locations, false).await;
+        let manifest = result.manifest.unwrap();
+        let components: Vec<_> = manifest.components.iter().map(|c| c.component).collect();
+        assert_eq!(
+            components,
+            vec![
+                StateComponent::Config,
+                StateComponent::TerminalProfiles,
+                StateComponent::CustomRules,
+                StateComponent::PluginData,
+            ]
+        );
+        let config = &manifest.components[0];
+        assert!(config.files.contains_key("config.toml"));
+        assert!(!config.files.contains_key("secrets.key"));
+        assert!(manifest.components[3]
+            .files
+            .contains_key("sample/data/state.json"));
+
+        let with_cache = FullBackupOptions {
+            include_download_cache: true,
+            dest_path: Some(dir.path().join("with-cache.zip").display().to_string()),
+            ..Default::default()
+        };
+        let result = create_full_backup(&locations, &with_cache, None)
+            .await
+            .unwrap();
+        assert!(result
+            .manifest
+            .unwrap()
+            .components
+            .iter()
+            .any(|c| c.component == StateComponent::DownloadCache));
+    }
+
+    #[tokio::test]
+    async fn test_inspect_reports_components_and_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let locations = locations(dir.path());
+        seed(&locations);
+        let result = create(&locations, false).await;
+
+        let inspection = inspect_full_backup(Path::new(&result.path)).await;
+        assert!(inspection.valid, "{:?}", inspection.issues);
+        let config = &inspection.components[0];
+        assert_eq!(config.component, StateComponent::Config);
+        assert_eq!(config.schema_version, CURRENT_SCHEMA_VERSION);
+        assert!(config.compatible);
+        assert!(!config.needs_migration);
+
+        let missing = inspect_full_backup(&dir.path().join("missing.zip")).await;
+        assert!(!missing.valid);
+        assert!(missing.manifest.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_selective_restore_only_replaces_selected_components() {
+        let dir = tempfile::tempdir().unwrap();
+        let locations = locations(dir.path());
+        seed(&locations);
+        let result = create(&locations, false).await;
+
+        let profiles = locations
+            .path(StateComponent::TerminalProfiles)
+            .join("profiles.json");
+        let plugin_state = locations
+            .path(StateComponent::PluginData)
+            .join("sample")
+            .join("data")
+            .join("state.json");
+        write(&profiles, "[{\"id\":\"changed\"}]");
+        write(&locations.path(StateComponent::CustomRules), "[{}]");
+        write(&plugin_state, "{\"count\":2}");
+
+        let restored = restore_full_backup(
+            Path::new(&result.path),
+            &[
+                StateComponent::TerminalProfiles,
+                StateComponent::CustomRules,
+            ],
+            &locations,
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(restored.success, "{:?}", restored.error);
+        assert_eq!(
+            restored.restored,
+            vec![
+                StateComponent::TerminalProfiles,
+                StateComponent::CustomRules
+            ]
+        );
+        assert!(!restored.restart_required);
+        assert_eq!(std::fs::read_to_string(&profiles).unwrap(), "[]");
+        assert_eq!(
+            std::fs::read_to_string(locations.path(StateComponent::CustomRules)).unwrap(),
+            "[]"
+        );
+        assert_eq!(
+            std::fs::read_to_string(&plugin_state).unwrap(),
+            "{\"count\":2}"
+        );
+
+        // No staging or previous copies are left behind
+        let leftovers: Vec<_> = std::fs::read_dir(&locations.root_dir)
+            .unwrap()
+            .flatten()
+            .filter(|e| e.file_name().to_string_lossy().starts_with('.'))
+            .collect();
+        assert!(leftovers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_restore_keeps_local_secrets_and_migrates_old_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let locations = locations(dir.path());
+        seed(&locations);
+        write(
+            &locations.config_dir.join("config.toml"),
+            "[provider_settings]\ndisabled_providers = [\"brew\"]\n",
+        );
+        let result = create(&locations, false).await;
+        let manifest = result.manifest.as_ref().unwrap();
+        assert_eq!(
+            manifest.components[0].schema_version,
+            UNVERSIONED_SCHEMA_VERSION
+        );
+
+        write(&locations.config_dir.join("secrets.key"), "rotated-key");
+        let restored = restore_full_backup(
+            Path::new(&result.path),
+            &[StateComponent::Config],
+            &locations,
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(restored.success, "{:?}", restored.error);
+        assert_eq!(
+            restored.config_migrated_from,
+            Some(UNVERSIONED_SCHEMA_VERSION)
+        );
+
+        let config = std::fs::read_to_string(locations.config_dir.join("config.toml")).unwrap();
+        let (settings, report) = Settings::parse_versioned(&config).unwrap();
+        assert!(!report.migrated());
+        assert_eq!(
+            settings.providers.get("brew").and_then(|p| p.enabled),
+            Some(false)
+        );
+        assert_eq!(
+            std::fs::read_to_string(locations.config_dir.join("secrets.key")).unwrap(),
+            "rotated-key"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_rejects_component_missing_from_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let locations = locations(dir.path());
+        seed(&locations);
+        let result = create(&locations, false).await;
+
+        let restored = restore_full_backup(
+            Path::new(&result.path),
+            &[StateComponent::DownloadCache],
+            &locations,
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(!restored.success);
+        assert_eq!(
+            restored.reason_code.as_deref(),
+            Some("component_not_in_backup")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_rejects_newer_schema_without_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let locations = locations(dir.path());
+        seed(&locations);
+        write(
+            &locations.config_dir.join("config.toml"),
+            &format!("schema_version = {}\n", CURRENT_SCHEMA_VERSION + 1),
+        );
+        let result = create(&locations, false).await;
+
+        write(&locations.config_dir.join("config.toml"), "local = true\n");
+        let restored = restore_full_backup(Path::new(&result.path), &[], &locations, None)
+            .await
+            .unwrap();
+        assert!(!restored.success);
+        assert_eq!(
+            restored.reason_code.as_deref(),
+            Some("component_schema_unsupported")
+        );
+        assert_eq!(
+            std::fs::read_to_string(locations.config_dir.join("config.toml")).unwrap(),
+            "local = true\n"
+        );
+    }
+}
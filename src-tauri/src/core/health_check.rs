@@ -2,8 +2,11 @@
     envvar_detect_conflicts, envvar_get_path, envvar_list_persistent_typed_summaries,
     EnvVarConflict, PathEntryInfo,
 };
+use crate::core::install_validation::{self, InstallProblem, InstallValidation};
+use crate::core::path_shadow::{self, BinaryShadowInfo, PathSource};
 use crate::error::{CogniaError, CogniaResult};
 use crate::platform::env::{current_platform, EnvVarScope};
+use crate::provider::cuda::{self, CudaCompatibility};
 use crate::provider::support::{
     classify_provider_scope, provider_health_probe_timeout, provider_timeout_reason,
     ProviderAvailabilityProbe, ProviderHealthScope,
@@ -12,7 +15,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::LazyLock;
 use std::time::{Duration, Instant};
@@ -510,6 +513,15 @@ struct HealthProviderEntry {
     is_api_provider: bool,
 }
 
+/// Wording for a CogniaLauncher vs. third-party shim precedence issue
+struct ShimOrderLabels {
+    manager: &'static str,
+    runtime: &'static str,
+    /// How users pick a version with the other manager
+    manager_selection: &'static str,
+    check_prefix: &'static str,
+}
+
 /// Health check manager
 pub struct HealthCheckManager {
     registry: Arc<RwLock<ProviderRegistry>>,
@@ -553,7 +565,7 @@ fn canonical_environment_types() -> &'static [&'static str] {
             "node", "python", "go", "rust", "ruby", "java", "kotlin", "scala", "groovy", "gradle",
             "maven", "php", "dotnet", "deno", "zig", "dart", "bun", "lua", "elixir", "erlang",
             "swift", "julia", "perl", "r", "haskell", "clojure", "crystal", "nim", "ocaml",
-            "fortran", "c", "cpp",
+            "fortran", "c", "cpp", "cuda",
         ]
     }
 
@@ -868,6 +880,31 @@ pub async fn check_environment(&self, env_type: &str) -> CogniaResult<Environmen
         let mut result = self.check_environment_health(&*provider).await;
         result.env_type = env_type.to_string();
         result.provider_id = Some(provider_id.clone());
+        if env_type == "python" {
+            if let Some(issue) = self.check_pyenv_shim_order().await {
+                result.add_issue(issue);
+            }
+        }
+        if let Ok(installed) = provider.list_installed_versions().await {
+            let logical = crate::core::EnvironmentManager::logical_env_type(env_type);
+            for validation in
+                install_validation::validate_installs(&logical, provider.as_ref(), &installed).await
+            {
+                if let Some(issue) = Self::build_broken_install_issue(&validation) {
+                    result.add_issue(issue);
+                }
+            }
+        }
+        if env_type == "cuda" {
+            if let Some(version) = result.current_version.clone() {
+                let driver = cuda::detect_driver().await;
+                let compatibility =
+                    cuda::check_compatibility(&version, driver.as_ref(), cfg!(windows));
+                if let Some(issue) = Self::build_cuda_driver_issue(&compatibility) {
+                    result.add_issue(issue);
+                }
+            }
+        }
         if available_candidates.len() > 1 {
             result.add_suggestion(format!(
                 "Multiple providers are available for {}: {}",
@@ -1416,6 +1453,16 @@ async fn check_system_health(&self, result: &mut SystemHealthResult) {
                 result.add_system_issue(issue);
             }
         }
+
+        // Check Cognia vs Volta shim precedence for Node.js
+        if let Some(issue) = self.check_volta_shim_order().await {
+            result.add_system_issue(issue);
+        }
+
+        // Check for managed binaries whose PATH copies disagree on version
+        for issue in self.check_shadowed_binaries().await {
+            result.add_system_issue(issue);
+        }
     }
 
     #[cfg(not(target_os = "windows"))]
@@ -1827,6 +1874,280 @@ async fn check_version_manager_conflicts(&self) -> Option<Vec<HealthIssue>> {
         }
     }
 
+    /// Check whether CogniaLauncher and Volta both shim Node.js on PATH
+    async fn check_volta_shim_order(&self) -> Option<HealthIssue> {
+        let volta_bin = crate::provider::volta::volta_bin_dir()?;
+        if !volta_bin.exists() {
+            return None;
+        }
+
+        let cognia_dirs =
+            Self::cognia_shim_dirs_with(&["node", "node.cmd", "node.exe", "npm", "npm.cmd"])?;
+        let path_var = std::env::var_os("PATH")?;
+        let entries: Vec<PathBuf> = std::env::split_paths(&path_var).collect();
+        Self::build_volta_shim_order_issue(&entries, &cognia_dirs, &volta_bin)
+    }
+
+    /// Check whether CogniaLauncher and pyenv both shim Python on PATH
+    async fn check_pyenv_shim_order(&self) -> Option<HealthIssue> {
+        let pyenv_shims = crate::provider::pyenv::pyenv_shims_dir()?;
+        if !pyenv_shims.exists() {
+            return None;
+        }
+
+        let cognia_dirs = Self::cognia_shim_dirs_with(&[
+            "python",
+            "python3",
+            "python.exe",
+            "python.cmd",
+            "pip",
+            "pip.cmd",
+        ])?;
+        let path_var = std::env::var_os("PATH")?;
+        let entries: Vec<PathBuf> = std::env::split_paths(&path_var).collect();
+        Self::build_pyenv_shim_order_issue(&entries, &cognia_dirs, &pyenv_shims)
+    }
+
+    /// Check whether PATH holds several versions of the same managed binary
+    async fn check_shadowed_binaries(&self) -> Vec<HealthIssue> {
+        let Some(path_var) = std::env::var_os("PATH") else {
+            return Vec::new();
+        };
+        let binaries = path_shadow::requested_binaries(None);
+        let context =
+            path_shadow::build_context(PathSource::Process, None, &path_var, &binaries).await;
+        Self::build_shadowed_binary_issues(&context.binaries)
+    }
+
+    fn build_shadowed_binary_issues(binaries: &[BinaryShadowInfo]) -> Vec<HealthIssue> {
+        binaries
+            .iter()
+            .filter_map(|info| {
+                let (winner, shadowed) = info.matches.split_first()?;
+                let winner_version = winner.version.as_deref()?;
+                let other = shadowed.iter().find(|found| {
+                    found
+                        .version
+                        .as_deref()
+                        .is_some_and(|version| version != winner_version)
+                })?;
+                Some(
+                    HealthIssue::new(
+                        Severity::Info,
+                        IssueCategory::PathConflict,
+                        format!(
+                            "{} on PATH resolves to {} and shadows other versions",
+                            info.name, winner.binary_path
+                        ),
+                    )
+                    .with_evidence(
+                        HealthSignalSource::RuntimeProbe,
+                        HealthEvidenceConfidence::Verified,
+                        format!("path_shadow:{}", info.name),
+                    )
+                    .with_details(format!(
+                        "'{}' ({}) comes before '{}' ({}). Reorder PATH if the wrong {} wins.",
+                        winner.binary_path,
+                        winner_version,
+                        other.binary_path,
+                        other.version.as_deref().unwrap_or_default(),
+                        info.name
+                    )),
+                )
+            })
+            .collect()
+    }
+
+    /// CogniaLauncher shim/bin directories that provide any of `bins`
+    fn cognia_shim_dirs_with(bins: &[&str]) -> Option<Vec<PathBuf>> {
+        let cognia_dir = crate::platform::fs::get_cognia_dir()?;
+        let dirs: Vec<PathBuf> = [cognia_dir.join("shims"), cognia_dir.join("bin")]
+            .into_iter()
+            .filter(|dir| bins.iter().any(|bin| dir.join(bin).exists()))
+            .collect();
+        (!dirs.is_empty()).then_some(dirs)
+    }
+
+    fn build_volta_shim_order_issue(
+        entries: &[PathBuf],
+        cognia_dirs: &[PathBuf],
+        volta_bin: &Path,
+    ) -> Option<HealthIssue> {
+        Self::build_shim_order_issue(
+            entries,
+            cognia_dirs,
+            volta_bin,
+            ShimOrderLabels {
+                manager: "Volta",
+                runtime: "Node.js",
+                manager_selection: "pinned with `volta pin`",
+                check_prefix: "volta_shim_order",
+            },
+        )
+    }
+
+    fn build_pyenv_shim_order_issue(
+        entries: &[PathBuf],
+        cognia_dirs: &[PathBuf],
+        pyenv_shims: &Path,
+    ) -> Option<HealthIssue> {
+        Self::build_shim_order_issue(
+            entries,
+            cognia_dirs,
+            pyenv_shims,
+            ShimOrderLabels {
+                manager: "pyenv",
+                runtime: "Python",
+                manager_selection: "selected with `pyenv local` or `pyenv global`",
+                check_prefix: "pyenv_shim_order",
+            },
+        )
+    }
+
+    fn build_shim_order_issue(
+        entries: &[PathBuf],
+        cognia_dirs: &[PathBuf],
+        manager_dir: &Path,
+        labels: ShimOrderLabels,
+    ) -> Option<HealthIssue> {
+        let normalize = |path: &Path| {
+            Self::normalize_envvar_path(&path.to_string_lossy())
+                .trim_end_matches(['/', '\\'])
+                .to_string()
+        };
+        let manager_key = normalize(manager_dir);
+        let cognia_keys: Vec<String> = cognia_dirs.iter().map(|dir| normalize(dir)).collect();
+
+        let manager_pos = entries
+            .iter()
+            .position(|entry| normalize(entry) == manager_key)?;
+        let cognia_pos = entries
+            .iter()
+            .position(|entry| cognia_keys.contains(&normalize(entry)))?;
+        let cognia_entry = entries[cognia_pos].display().to_string();
+        let manager_entry = manager_dir.display().to_string();
+
+        let (message, details, check_id) = if cognia_pos < manager_pos {
+            (
+                format!(
+                    "CogniaLauncher shims take precedence over {} shims on PATH",
+                    labels.manager
+                ),
+                format!(
+                    "'{}' comes before '{}', so {} versions {} are ignored.",
+                    cognia_entry, manager_entry, labels.runtime, labels.manager_selection
+                ),
+                format!("{}:cognia_first", labels.check_prefix),
+            )
+        } else {
+            (
+                format!(
+                    "{} shims take precedence over CogniaLauncher shims on PATH",
+                    labels.manager
+                ),
+                format!(
+                    "'{}' comes before '{}', so {} versions selected in CogniaLauncher are ignored.",
+                    manager_entry, cognia_entry, labels.runtime
+                ),
+                format!("{}:{}_first", labels.check_prefix, labels.manager.to_lowercase()),
+            )
+        };
+
+        Some(
+            HealthIssue::new(Severity::Warning, IssueCategory::PathConflict, message)
+                .with_evidence(
+                    HealthSignalSource::SystemProbe,
+                    HealthEvidenceConfidence::Verified,
+                    check_id,
+                )
+                .with_details(format!(
+                    "{} Keep only one of them on PATH for {}, or put the one you want first.",
+                    details, labels.runtime
+                )),
+        )
+    }
+
+    /// Report a CUDA toolkit on PATH that the installed NVIDIA driver cannot run
+    fn build_broken_install_issue(validation: &InstallValidation) -> Option<HealthIssue> {
+        let category = match validation.problems.first()? {
+            InstallProblem::VersionMismatch { .. } => IssueCategory::VersionMismatch,
+            InstallProblem::NotExecutable { .. } => IssueCategory::PermissionError,
+            _ => IssueCategory::MissingDependency,
+        };
+        Some(
+            HealthIssue::new(
+                Severity::Warning,
+                category,
+                format!(
+                    "{} {} is a broken or partial install",
+                    validation.env_type, validation.version
+                ),
+            )
+            .with_evidence(
+                HealthSignalSource::RuntimeProbe,
+                HealthEvidenceConfidence::Verified,
+                format!(
+                    "install_integrity:{}:{}",
+                    validation.env_type, validation.version
+                ),
+            )
+            .with_details(format!(
+                "{} ({}). Remove it and reinstall the version.",
+                validation.summary(),
+                validation.install_path.display()
+            )),
+        )
+    }
+
+    fn build_cuda_driver_issue(compatibility: &CudaCompatibility) -> Option<HealthIssue> {
+        if compatibility.compatible {
+            return None;
+        }
+
+        let version = &compatibility.requested_version;
+        let Some(driver) = &compatibility.driver else {
+            return Some(
+                HealthIssue::new(
+                    Severity::Warning,
+                    IssueCategory::MissingDependency,
+                    format!("No NVIDIA driver found for CUDA {}", version),
+                )
+                .with_evidence(
+                    HealthSignalSource::RuntimeProbe,
+                    HealthEvidenceConfidence::Verified,
+                    "cuda_driver:missing",
+                )
+                .with_details(
+                    "nvidia-smi could not be run. CUDA programs need an NVIDIA GPU and driver.",
+                ),
+            );
+        };
+
+        let remedy = match &compatibility.required_driver_version {
+            Some(required) => format!(
+                "Update the NVIDIA driver to {} or newer, or switch to an older CUDA toolkit.",
+                required
+            ),
+            None => "Update the NVIDIA driver or switch to an older CUDA toolkit.".to_string(),
+        };
+        Some(
+            HealthIssue::new(
+                Severity::Error,
+                IssueCategory::VersionMismatch,
+                format!(
+                    "NVIDIA driver {} cannot run CUDA {}",
+                    driver.driver_version, version
+                ),
+            )
+            .with_evidence(
+                HealthSignalSource::RuntimeProbe,
+                HealthEvidenceConfidence::Verified,
+                "cuda_driver:incompatible",
+            )
+            .with_details(format!("{}. {}", compatibility.message, remedy)),
+        )
+    }
+
     /// Check a specific environment provider's health
     async fn check_environment_health(
         &self,
@@ -2405,6 +2726,8 @@ fn provider_to_env_type(&self, provider_id: &str) -> String {
             "dotnet" => "dotnet".to_string(),
             "zig" => "zig".to_string(),
             "fvm" => "dart".to_string(),
+            "juliaup" => "julia".to_string(),
+            "rig" => "r".to_string(),
             "system-c" => "c".to_string(),
             "system-cpp" => "cpp".to_string(),
             _ => provider_id.to_string(),
@@ -2478,6 +2801,22 @@ fn get_install_command(&self, provider_id: &str) -> String {
             }
             "zig" => "Download from https://ziglang.org/download/".to_string(),
             "fvm" => "dart pub global activate fvm".to_string(),
+            "juliaup" => {
+                if cfg!(windows) {
+                    "winget install --name Julia --id 9NJNWW8PVKMN -e -s msstore".to_string()
+                } else {
+                    "curl -fsSL https://install.julialang.org | sh".to_string()
+                }
+            }
+            "rig" => {
+                if cfg!(windows) {
+                    "winget install posit.rig".to_string()
+                } else if cfg!(target_os = "macos") {
+                    "brew tap r-lib/rig && brew install --cask rig".to_string()
+                } else {
+                    "curl -Ls https://github.com/r-lib/rig/releases/download/latest/rig-linux-$(arch)-latest.tar.gz | sudo tar xz -C /usr/local".to_string()
+                }
+            }
             "git" => {
                 if cfg!(windows) {
                     "winget install Git.Git".to_string()
@@ -2535,6 +2874,7 @@ fn get_expected_path_patterns(&self, provider_id: &str) -> Vec<String> {
             "dotnet" => vec!["dotnet".to_string()],
             "zig" => vec![".zig".to_string()],
             "fvm" => vec![".fvm".to_string(), "fvm".to_string()],
+            "juliaup" => vec![".juliaup".to_string(), ".julia".to_string()],
             "system-lua" => vec!["lua".to_string()],
             "system-c" | "system-cpp" => vec![],
             _ => vec![],
@@ -2564,6 +2904,8 @@ fn get_shell_setup_command(&self, provider_id: &str) -> String {
             "phpbrew" => "source ~/.phpbrew/bashrc".to_string(),
             "zig" => "export PATH=\"$HOME/.zig/current:$PATH\"".to_string(),
             "fvm" => "export PATH=\"$HOME/fvm/default/bin:$PATH\"".to_string(),
+            "juliaup" => "export PATH=\"$HOME/.juliaup/bin:$PATH\"".to_string(),
+            "rig" => "# rig links the default R into PATH automatically".to_string(),
             "system-lua" => "eval $(luarocks path)".to_string(),
             "system-c" | "system-cpp" => "# System compilers are available in PATH by default".to_string(),
             _ => format!("# Configure {} in your shell", provider_id),
@@ -3131,6 +3473,154 @@ fn test_system_result_inferred_warning_issue_is_advisory() {
         assert_eq!(system.system_issues.len(), 1);
     }
 
+    #[test]
+    fn test_build_volta_shim_order_issue_reports_precedence() {
+        let cognia = PathBuf::from("/home/u/.CogniaLauncher/shims");
+        let volta = PathBuf::from("/home/u/.volta/bin");
+        let usr = PathBuf::from("/usr/bin");
+
+        let issue = HealthCheckManager::build_volta_shim_order_issue(
+            &[usr.clone(), volta.clone(), cognia.clone()],
+            std::slice::from_ref(&cognia),
+            &volta,
+        )
+        .unwrap();
+        assert!(issue.message.starts_with("Volta shims take precedence"));
+        assert_eq!(
+            issue.check_id.as_deref(),
+            Some("volta_shim_order:volta_first")
+        );
+
+        let issue = HealthCheckManager::build_volta_shim_order_issue(
+            &[cognia.clone(), PathBuf::from("/home/u/.volta/bin/")],
+            std::slice::from_ref(&cognia),
+            &volta,
+        )
+        .unwrap();
+        assert_eq!(
+            issue.check_id.as_deref(),
+            Some("volta_shim_order:cognia_first")
+        );
+
+        assert!(HealthCheckManager::build_volta_shim_order_issue(
+            &[usr, volta.clone()],
+            std::slice::from_ref(&cognia),
+            &volta,
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_build_shadowed_binary_issues_needs_differing_versions() {
+        let found = |path: &str, version: Option<&str>, wins: bool| path_shadow::ShadowMatch {
+            path_index: 0,
+            dir: String::new(),
+            binary_path: path.to_string(),
+            version: version.map(str::to_string),
+            wins,
+        };
+        let binaries = vec![
+            BinaryShadowInfo {
+                name: "python".into(),
+                matches: vec![
+                    found("/usr/bin/python", Some("3.10.12"), true),
+                    found("/home/u/.pyenv/shims/python", Some("3.12.1"), false),
+                ],
+                shadowed: true,
+            },
+            BinaryShadowInfo {
+                name: "node".into(),
+                matches: vec![
+                    found("/usr/local/bin/node", Some("20.11.0"), true),
+                    found("/usr/bin/node", Some("20.11.0"), false),
+                ],
+                shadowed: true,
+            },
+        ];
+
+        let issues = HealthCheckManager::build_shadowed_binary_issues(&binaries);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].check_id.as_deref(), Some("path_shadow:python"));
+        assert!(issues[0]
+            .details
+            .as_deref()
+            .unwrap()
+            .contains("/home/u/.pyenv/shims/python"));
+    }
+
+    #[test]
+    fn test_build_cuda_driver_issue() {
+        let old_driver = cuda::NvidiaDriverInfo {
+            driver_version: "535.54.03".into(),
+            max_cuda_version: Some("12.2".into()),
+        };
+        let issue = HealthCheckManager::build_cuda_driver_issue(&cuda::check_compatibility(
+            "12.4.131",
+            Some(&old_driver),
+            false,
+        ))
+        .unwrap();
+        assert_eq!(issue.severity, Severity::Error);
+        assert_eq!(issue.check_id.as_deref(), Some("cuda_driver:incompatible"));
+        assert!(issue.details.as_deref().unwrap().contains("550.54.14"));
+
+        let issue = HealthCheckManager::build_cuda_driver_issue(&cuda::check_compatibility(
+            "12.4", None, false,
+        ))
+        .unwrap();
+        assert_eq!(issue.check_id.as_deref(), Some("cuda_driver:missing"));
+
+        assert!(
+            HealthCheckManager::build_cuda_driver_issue(&cuda::check_compatibility(
+                "12.2",
+                Some(&old_driver),
+                false,
+            ))
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_build_pyenv_shim_order_issue_reports_precedence() {
+        let cognia = PathBuf::from("/home/u/.CogniaLauncher/shims");
+        let pyenv = PathBuf::from("/home/u/.pyenv/shims");
+
+        let issue = HealthCheckManager::build_pyenv_shim_order_issue(
+            &[pyenv.clone(), PathBuf::from("/usr/bin"), cognia.clone()],
+            std::slice::from_ref(&cognia),
+            &pyenv,
+        )
+        .unwrap();
+        assert_eq!(
+            issue.message,
+            "pyenv shims take precedence over CogniaLauncher shims on PATH"
+        );
+        assert_eq!(
+            issue.check_id.as_deref(),
+            Some("pyenv_shim_order:pyenv_first")
+        );
+        assert!(issue.details.as_deref().unwrap().contains("Python"));
+
+        let issue = HealthCheckManager::build_pyenv_shim_order_issue(
+            &[cognia.clone(), pyenv.clone()],
+            std::slice::from_ref(&cognia),
+            &pyenv,
+        )
+        .unwrap();
+        assert_eq!(
+            issue.check_id.as_deref(),
+            Some("pyenv_shim_order:cognia_first")
+        );
+        assert!(issue.details.as_deref().unwrap().contains("pyenv local"));
+
+        assert!(HealthCheckManager::build_pyenv_shim_order_issue(
+            &[cognia.clone()],
+            std::slice::from_ref(&cognia),
+            &pyenv,
+        )
+        .is_none());
+    }
+
     #[test]
     fn test_build_envvar_path_validity_issue_reports_missing_entries() {
         let issue = HealthCheckManager::build_envvar_path_validity_issue(
@@ -10,6 +10,8 @@
 
 const MAX_HISTORY_ENTRIES: usize = 1000;
 const HISTORY_READ_CACHE_TTL: Duration = Duration::from_secs(2);
+/// Error message of an update entry whose attempt has not finished
+pub const UPDATE_IN_PROGRESS: &str = "Update in progress";
 static HISTORY_MUTATION_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 static HISTORY_READ_CACHE: Lazy<StdMutex<Option<(Instant, InstallHistory)>>> =
     Lazy::new(|| StdMutex::new(None));
@@ -24,6 +26,9 @@ pub struct InstallHistoryEntry {
     pub provider: String,
     pub success: bool,
     pub error_message: Option<String>,
+    /// Plugin that requested the operation; `None` when started from the UI
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor_plugin_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -174,6 +179,37 @@ pub fn get_last_successful_version(&self, name: &str) -> Option<&str> {
             .next()
     }
 
+    /// Version a package had before its most recent recorded update
+    /// (update entries store their version as `old -> new`)
+    pub fn get_version_before_update(&self, name: &str, provider: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .filter(|e| {
+                e.action == HistoryAction::Update
+                    && e.name.eq_ignore_ascii_case(name)
+                    && e.provider.eq_ignore_ascii_case(provider)
+            })
+            .find_map(|e| e.version.split_once(" -> ").map(|(old, _)| old.trim()))
+            .filter(|old| !old.is_empty())
+    }
+
+    /// Mark a previously added entry as finished
+    pub fn complete_entry(
+        &mut self,
+        id: &str,
+        success: bool,
+        error_message: Option<String>,
+    ) -> bool {
+        match self.entries.iter_mut().find(|e| e.id == id) {
+            Some(entry) => {
+                entry.success = success;
+                entry.error_message = error_message;
+                true
+            }
+            None => false,
+        }
+    }
+
     pub fn clear(&mut self) {
         self.entries.clear();
     }
@@ -248,6 +284,18 @@ pub async fn record_install(
         provider: &str,
         success: bool,
         error_message: Option<String>,
+    ) -> CogniaResult<()> {
+        Self::record_install_by(name, version, provider, success, error_message, None).await
+    }
+
+    /// Record an install attributed to the plugin that requested it
+    pub async fn record_install_by(
+        name: &str,
+        version: &str,
+        provider: &str,
+        success: bool,
+        error_message: Option<String>,
+        actor_plugin_id: Option<&str>,
     ) -> CogniaResult<()> {
         let entry = InstallHistoryEntry {
             id: uuid::Uuid::new_v4().to_string(),
@@ -258,6 +306,7 @@ pub async fn record_install(
             provider: provider.to_string(),
             success,
             error_message,
+            actor_plugin_id: actor_plugin_id.map(str::to_string),
         };
 
         Self::append_entry(entry).await
@@ -279,6 +328,7 @@ pub async fn record_uninstall(
             provider: provider.to_string(),
             success,
             error_message,
+            actor_plugin_id: None,
         };
 
         Self::append_entry(entry).await
@@ -301,11 +351,61 @@ pub async fn record_update(
             provider: provider.to_string(),
             success,
             error_message,
+            actor_plugin_id: None,
         };
 
         Self::append_entry(entry).await
     }
 
+    /// Record an update before it is attempted so the previous version is
+    /// known even if the update is interrupted. The entry stays unsuccessful
+    /// until [`HistoryManager::complete_entry`] is called with its id.
+    pub async fn begin_update(
+        name: &str,
+        old_version: &str,
+        new_version: &str,
+        provider: &str,
+    ) -> CogniaResult<String> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let entry = InstallHistoryEntry {
+            id: id.clone(),
+            name: name.to_string(),
+            version: format!("{} -> {}", old_version, new_version),
+            action: HistoryAction::Update,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            provider: provider.to_string(),
+            success: false,
+            error_message: Some(UPDATE_IN_PROGRESS.to_string()),
+            actor_plugin_id: None,
+        };
+
+        Self::append_entry(entry).await?;
+        Ok(id)
+    }
+
+    pub async fn complete_entry(
+        id: &str,
+        success: bool,
+        error_message: Option<String>,
+    ) -> CogniaResult<()> {
+        let _guard = HISTORY_MUTATION_LOCK.lock().await;
+        let mut history = InstallHistory::load().await?;
+        if history.complete_entry(id, success, error_message) {
+            history.save().await?;
+        }
+        Ok(())
+    }
+
+    pub async fn get_version_before_update(
+        name: &str,
+        provider: &str,
+    ) -> CogniaResult<Option<String>> {
+        let history = InstallHistory::load().await?;
+        Ok(history
+            .get_version_before_update(name, provider)
+            .map(|v| v.to_string()))
+    }
+
     pub async fn record_rollback(
         name: &str,
         to_version: &str,
@@ -322,6 +422,7 @@ pub async fn record_rollback(
             provider: provider.to_string(),
             success,
             error_message,
+            actor_plugin_id: None,
         };
 
         Self::append_entry(entry).await
@@ -398,6 +499,7 @@ fn test_history_entry_creation() {
             provider: "npm".to_string(),
             success: true,
             error_message: None,
+            actor_plugin_id: None,
         };
 
         assert_eq!(entry.name, "test-package");
@@ -418,6 +520,7 @@ fn test_history_max_entries() {
                 provider: "test".to_string(),
                 success: true,
                 error_message: None,
+                actor_plugin_id: None,
             });
         }
 
@@ -519,6 +622,7 @@ fn test_install_history_entry_serde_roundtrip() {
             provider: "npm".to_string(),
             success: true,
             error_message: None,
+            actor_plugin_id: None,
         };
 
         let json = serde_json::to_string(&entry).unwrap();
@@ -540,6 +644,7 @@ fn test_install_history_entry_with_error() {
             provider: "npm".to_string(),
             success: false,
             error_message: Some("network timeout".to_string()),
+            actor_plugin_id: None,
         };
 
         let json = serde_json::to_string(&entry).unwrap();
@@ -571,6 +676,7 @@ fn test_history_add_entry_prepends() {
             provider: "npm".to_string(),
             success: true,
             error_message: None,
+            actor_plugin_id: None,
         });
 
         history.add_entry(InstallHistoryEntry {
@@ -582,6 +688,7 @@ fn test_history_add_entry_prepends() {
             provider: "npm".to_string(),
             success: true,
             error_message: None,
+            actor_plugin_id: None,
         });
 
         assert_eq!(history.entries.len(), 2);
@@ -602,6 +709,7 @@ fn test_get_entries_with_limit() {
                 provider: "npm".to_string(),
                 success: true,
                 error_message: None,
+                actor_plugin_id: None,
             });
         }
 
@@ -628,6 +736,7 @@ fn test_get_package_history() {
             provider: "npm".to_string(),
             success: true,
             error_message: None,
+            actor_plugin_id: None,
         });
 
         history.add_entry(InstallHistoryEntry {
@@ -639,6 +748,7 @@ fn test_get_package_history() {
             provider: "npm".to_string(),
             success: true,
             error_message: None,
+            actor_plugin_id: None,
         });
 
         history.add_entry(InstallHistoryEntry {
@@ -650,6 +760,7 @@ fn test_get_package_history() {
             provider: "npm".to_string(),
             success: true,
             error_message: None,
+            actor_plugin_id: None,
         });
 
         let lodash_history = history.get_package_history("lodash");
@@ -675,6 +786,7 @@ fn test_get_last_successful_version() {
             provider: "npm".to_string(),
             success: true,
             error_message: None,
+            actor_plugin_id: None,
         });
 
         history.add_entry(InstallHistoryEntry {
@@ -686,6 +798,7 @@ fn test_get_last_successful_version() {
             provider: "npm".to_string(),
             success: false,
             error_message: Some("failed".into()),
+            actor_plugin_id: None,
         });
 
         // Most recent successful install (prepended order)
@@ -695,6 +808,47 @@ fn test_get_last_successful_version() {
         assert!(history.get_last_successful_version("nonexistent").is_none());
     }
 
+    #[test]
+    fn test_get_version_before_update_and_complete_entry() {
+        let mut history = InstallHistory::new();
+
+        history.add_entry(InstallHistoryEntry {
+            id: "1".to_string(),
+            name: "ripgrep".to_string(),
+            version: "13.0.0 -> 14.0.0".to_string(),
+            action: HistoryAction::Update,
+            timestamp: "2024-01-01T00:00:00Z".to_string(),
+            provider: "cargo".to_string(),
+            success: true,
+            error_message: None,
+            actor_plugin_id: None,
+        });
+        history.add_entry(InstallHistoryEntry {
+            id: "2".to_string(),
+            name: "ripgrep".to_string(),
+            version: "14.0.0 -> 14.1.0".to_string(),
+            action: HistoryAction::Update,
+            timestamp: "2024-02-01T00:00:00Z".to_string(),
+            provider: "cargo".to_string(),
+            success: false,
+            error_message: Some(UPDATE_IN_PROGRESS.to_string()),
+            actor_plugin_id: None,
+        });
+
+        assert_eq!(
+            history.get_version_before_update("ripgrep", "cargo"),
+            Some("14.0.0")
+        );
+        assert!(history
+            .get_version_before_update("ripgrep", "brew")
+            .is_none());
+
+        assert!(history.complete_entry("2", true, None));
+        assert!(history.entries[0].success);
+        assert!(history.entries[0].error_message.is_none());
+        assert!(!history.complete_entry("missing", true, None));
+    }
+
     #[test]
     fn test_get_last_successful_version_ignores_uninstall() {
         let mut history = InstallHistory::new();
@@ -708,6 +862,7 @@ fn test_get_last_successful_version_ignores_uninstall() {
             provider: "npm".to_string(),
             success: true,
             error_message: None,
+            actor_plugin_id: None,
         });
 
         // Uninstall actions should be ignored
@@ -727,6 +882,7 @@ fn test_history_clear() {
                 provider: "test".to_string(),
                 success: true,
                 error_message: None,
+                actor_plugin_id: None,
             });
         }
 
@@ -747,6 +903,7 @@ fn test_install_history_serde_roundtrip() {
             provider: "npm".to_string(),
             success: true,
             error_message: None,
+            actor_plugin_id: None,
         });
 
         let json = serde_json::to_string(&history).unwrap();
@@ -767,6 +924,7 @@ fn test_query_entries_with_filters() {
             provider: "npm".to_string(),
             success: true,
             error_message: None,
+            actor_plugin_id: None,
         });
         history.add_entry(InstallHistoryEntry {
             id: "2".to_string(),
@@ -777,6 +935,7 @@ fn test_query_entries_with_filters() {
             provider: "pip".to_string(),
             success: false,
             error_message: Some("network timeout".into()),
+            actor_plugin_id: None,
         });
         history.add_entry(InstallHistoryEntry {
             id: "3".to_string(),
@@ -787,6 +946,7 @@ fn test_query_entries_with_filters() {
             provider: "npm".to_string(),
             success: true,
             error_message: None,
+            actor_plugin_id: None,
         });
 
         let npm_entries = history.query_entries(&HistoryQuery {
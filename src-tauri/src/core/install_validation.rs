@@ -0,0 +1,728 @@
+use crate::core::shim::ShimManager;
+use crate::error::CogniaResult;
+use crate::platform::process::{self, ProcessError, ProcessOptions};
+use crate::provider::{EnvironmentProvider, InstalledVersion};
+use futures::stream::{self, StreamExt};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Installs probed at once; each probe starts up to two processes
+const MAX_CONCURRENT_PROBES: usize = 4;
+static VERSION_TOKEN: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d+(?:\.\d+)+").unwrap());
+
+/// Why an installed version is considered broken
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum InstallProblem {
+    MissingDirectory,
+    EmptyDirectory,
+    MissingBinary { binary: String },
+    NotExecutable { path: PathBuf },
+    BinaryFailed { binary: String, message: String },
+    VersionMismatch { expected: String, reported: String },
+    SupportToolBroken { tool: String, message: String },
+}
+
+impl std::fmt::Display for InstallProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingDirectory => write!(f, "install directory is missing"),
+            Self::EmptyDirectory => write!(f, "install directory is empty"),
+            Self::MissingBinary { binary } => write!(f, "`{}` binary is missing", binary),
+            Self::NotExecutable { path } => write!(f, "{} is not executable", path.display()),
+            Self::BinaryFailed { binary, message } => {
+                write!(f, "`{}` failed to run: {}", binary, message)
+            }
+            Self::VersionMismatch { expected, reported } => {
+                write!(f, "binary reports {} instead of {}", reported, expected)
+            }
+            Self::SupportToolBroken { tool, message } => {
+                write!(f, "`{}` does not work: {}", tool, message)
+            }
+        }
+    }
+}
+
+/// Integrity check result for one installed version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallValidation {
+    pub env_type: String,
+    pub version: String,
+    pub install_path: PathBuf,
+    pub problems: Vec<InstallProblem>,
+    /// The problems prove the install is unusable, so it may be removed
+    /// without asking. Doubtful findings (an unexpected exit code, a version
+    /// string that does not parse the same way, a binary missing from a
+    /// guessed location) are only reported.
+    pub removable: bool,
+}
+
+impl InstallValidation {
+    pub fn is_broken(&self) -> bool {
+        !self.problems.is_empty()
+    }
+
+    /// Problems joined into one human-readable line
+    pub fn summary(&self) -> String {
+        self.problems
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/// A tool that ships with the runtime and must work for the install to be usable
+struct SupportTool {
+    name: &'static str,
+    /// `None` runs the main binary with `args` (e.g. `python -m pip`)
+    binary: Option<&'static str>,
+    args: &'static [&'static str],
+}
+
+struct InstallSpec {
+    binaries: &'static [&'static str],
+    version_args: &'static [&'static str],
+    support: Option<SupportTool>,
+}
+
+fn install_spec(env_type: &str) -> Option<InstallSpec> {
+    let spec = match env_type {
+        "node" => InstallSpec {
+            binaries: &["node"],
+            version_args: &["--version"],
+            support: Some(SupportTool {
+                name: "npm",
+                binary: Some("npm"),
+                args: &["--version"],
+            }),
+        },
+        "python" => InstallSpec {
+            binaries: &["python3", "python"],
+            version_args: &["--version"],
+            support: Some(SupportTool {
+                name: "pip",
+                binary: None,
+                args: &["-m", "pip", "--version"],
+            }),
+        },
+        "ruby" => InstallSpec {
+            binaries: &["ruby"],
+            version_args: &["--version"],
+            support: Some(SupportTool {
+                name: "gem",
+                binary: Some("gem"),
+                args: &["--version"],
+            }),
+        },
+        "rust" => InstallSpec {
+            binaries: &["rustc"],
+            version_args: &["--version"],
+            support: Some(SupportTool {
+                name: "cargo",
+                binary: Some("cargo"),
+                args: &["--version"],
+            }),
+        },
+        "go" => InstallSpec {
+            binaries: &["go"],
+            version_args: &["version"],
+            support: None,
+        },
+        "java" => InstallSpec {
+            binaries: &["java"],
+            version_args: &["-version"],
+            support: None,
+        },
+        "kotlin" => InstallSpec {
+            binaries: &["kotlinc", "kotlin"],
+            version_args: &["-version"],
+            support: None,
+        },
+        "scala" => InstallSpec {
+            binaries: &["scala"],
+            version_args: &["-version"],
+            support: None,
+        },
+        "deno" => InstallSpec {
+            binaries: &["deno"],
+            version_args: &["--version"],
+            support: None,
+        },
+        "bun" => InstallSpec {
+            binaries: &["bun"],
+            version_args: &["--version"],
+            support: None,
+        },
+        "zig" => InstallSpec {
+            binaries: &["zig"],
+            version_args: &["version"],
+            support: None,
+        },
+        "php" => InstallSpec {
+            binaries: &["php"],
+            version_args: &["--version"],
+            support: None,
+        },
+        "dotnet" => InstallSpec {
+            binaries: &["dotnet"],
+            version_args: &["--version"],
+            support: None,
+        },
+        _ => return None,
+    };
+    Some(spec)
+}
+
+/// Where binaries usually live when the provider cannot say
+fn fallback_dirs(install_path: &Path) -> Vec<PathBuf> {
+    vec![
+        install_path.to_path_buf(),
+        install_path.join("bin"),
+        install_path.join("Scripts"),
+    ]
+}
+
+fn candidate_names(binary: &str) -> Vec<String> {
+    if cfg!(windows) {
+        ["exe", "cmd", "bat"]
+            .iter()
+            .map(|ext| format!("{}.{}", binary, ext))
+            .collect()
+    } else {
+        vec![binary.to_string()]
+    }
+}
+
+/// First file named like `binary` in `dirs`
+fn find_binary(dirs: &[PathBuf], binary: &str) -> Option<PathBuf> {
+    dirs.iter().find_map(|dir| {
+        candidate_names(binary)
+            .into_iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+    })
+}
+
+/// The directories the provider puts on PATH for `version`, i.e. where its
+/// own lookup finds the executables. `None` when the provider cannot say.
+pub fn provider_bin_dirs(
+    provider: &dyn EnvironmentProvider,
+    version: &str,
+) -> Option<Vec<PathBuf>> {
+    provider
+        .get_env_modifications(version)
+        .ok()
+        .map(|mods| mods.path_prepend)
+        .filter(|dirs| !dirs.is_empty())
+}
+
+fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::metadata(path)
+            .map(|meta| meta.permissions().mode() & 0o111 != 0)
+            .unwrap_or(false)
+    }
+    #[cfg(windows)]
+    {
+        path.is_file()
+    }
+}
+
+/// Strip a leading `v`/`go`/`jdk-` style prefix so directory names compare
+/// against what the binary prints
+fn normalize_version(version: &str) -> &str {
+    ["jdk-", "jdk", "go", "v"]
+        .iter()
+        .find_map(|prefix| version.strip_prefix(prefix))
+        .unwrap_or(version)
+}
+
+/// Whether the version a binary printed agrees with the installed version.
+/// One may carry more components than the other (`21` vs `21.0.2+13`).
+pub fn version_matches_output(expected: &str, output: &str) -> Option<bool> {
+    let expected = normalize_version(expected);
+    if !expected.starts_with(|c: char| c.is_ascii_digit()) {
+        // Named toolchains such as `stable-x86_64-...` cannot be compared
+        return None;
+    }
+    let reported = VERSION_TOKEN.find(output)?.as_str();
+    let expected_parts: Vec<&str> = expected
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|p| !p.is_empty())
+        .collect();
+    let reported_parts: Vec<&str> = reported.split('.').collect();
+    let common = expected_parts.len().min(reported_parts.len());
+    Some(
+        expected_parts[..common]
+            .iter()
+            .zip(&reported_parts[..common])
+            .all(|(a, b)| a.trim_start_matches('0') == b.trim_start_matches('0')),
+    )
+}
+
+/// Java 8 and older report `1.8.0_392`, newer releases `21.0.2`, and
+/// installs are named either way (`8u392-b08`, `jdk-21.0.2+13`), so only
+/// the feature release is compared.
+pub fn java_version_matches_output(expected: &str, output: &str) -> Option<bool> {
+    let feature_release = |version: &str| -> Option<u64> {
+        let mut parts = version
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|p| !p.is_empty());
+        let first: u64 = parts.next()?.parse().ok()?;
+        if first == 1 {
+            parts.next()?.parse().ok()
+        } else {
+            Some(first)
+        }
+    };
+    let expected = feature_release(normalize_version(expected))?;
+    let reported = feature_release(VERSION_TOKEN.find(output)?.as_str())?;
+    Some(expected == reported)
+}
+
+fn versions_agree(env_type: &str, expected: &str, output: &str) -> Option<bool> {
+    if env_type == "java" {
+        java_version_matches_output(expected, output)
+    } else {
+        version_matches_output(expected, output)
+    }
+}
+
+fn probe_options(path: &Path) -> ProcessOptions {
+    let mut options = ProcessOptions::new().with_timeout(PROBE_TIMEOUT);
+    // Launcher scripts such as npm resolve their runtime through PATH
+    if let Some(dir) = path.parent() {
+        let mut paths = vec![dir.to_path_buf()];
+        if let Some(current) = std::env::var_os("PATH") {
+            paths.extend(std::env::split_paths(&current));
+        }
+        if let Ok(joined) = std::env::join_paths(paths) {
+            options = options.with_env("PATH", joined.to_string_lossy().to_string());
+        }
+    }
+    options
+}
+
+struct ProbeFailure {
+    message: String,
+    /// The binary could not be started or crashed, as opposed to exiting
+    /// with an error or timing out
+    conclusive: bool,
+}
+
+/// Run `path args` and return its combined output, or why it failed
+async fn probe(path: &Path, args: &[&str]) -> Result<String, ProbeFailure> {
+    let program = path.to_string_lossy();
+    match process::execute(&program, args, Some(probe_options(path))).await {
+        Ok(output) if output.success => Ok(format!("{}\n{}", output.stdout, output.stderr)),
+        Ok(output) => {
+            let stderr = output.stderr.trim();
+            Err(ProbeFailure {
+                message: if stderr.is_empty() {
+                    format!("exited with code {}", output.exit_code)
+                } else {
+                    stderr.lines().last().unwrap_or(stderr).to_string()
+                },
+                // 126/127: the loader rejected it; negative: killed by a signal
+                conclusive: output.exit_code < 0 || matches!(output.exit_code, 126 | 127),
+            })
+        }
+        Err(e) => Err(ProbeFailure {
+            conclusive: !matches!(e, ProcessError::Timeout(_)),
+            message: e.to_string(),
+        }),
+    }
+}
+
+/// Check one installed version: its directory, main binary, reported version
+/// and bundled support tool. `bin_dirs` is where the provider says the
+/// version's executables are (see [`provider_bin_dirs`]); without it only a
+/// guess is searched and a missing binary is not conclusive. Env types
+/// without a known layout only get the directory checks.
+pub async fn validate_install(
+    env_type: &str,
+    installed: &InstalledVersion,
+    bin_dirs: Option<&[PathBuf]>,
+) -> InstallValidation {
+    let mut validation = InstallValidation {
+        env_type: env_type.to_string(),
+        version: installed.version.clone(),
+        install_path: installed.install_path.clone(),
+        problems: Vec::new(),
+        removable: false,
+    };
+    let path = &installed.install_path;
+
+    if !path.is_dir() {
+        validation.problems.push(InstallProblem::MissingDirectory);
+        validation.removable = true;
+        return validation;
+    }
+    if std::fs::read_dir(path)
+        .map(|mut entries| entries.next().is_none())
+        .unwrap_or(true)
+    {
+        validation.problems.push(InstallProblem::EmptyDirectory);
+        validation.removable = true;
+        return validation;
+    }
+
+    let Some(spec) = install_spec(env_type) else {
+        return validation;
+    };
+
+    let lookup_known = bin_dirs.is_some_and(|dirs| !dirs.is_empty());
+    let mut dirs = bin_dirs.map(<[PathBuf]>::to_vec).unwrap_or_default();
+    dirs.extend(fallback_dirs(path));
+
+    let Some(binary) = spec
+        .binaries
+        .iter()
+        .find_map(|name| find_binary(&dirs, name))
+    else {
+        validation.problems.push(InstallProblem::MissingBinary {
+            binary: spec.binaries[0].to_string(),
+        });
+        validation.removable = lookup_known;
+        return validation;
+    };
+    if !is_executable(&binary) {
+        validation
+            .problems
+            .push(InstallProblem::NotExecutable { path: binary });
+        validation.removable = true;
+        return validation;
+    }
+
+    match probe(&binary, spec.version_args).await {
+        Ok(output) => {
+            if versions_agree(env_type, &installed.version, &output) == Some(false) {
+                let reported = VERSION_TOKEN
+                    .find(&output)
+                    .map(|m| m.as_str().to_string())
+                    .unwrap_or_default();
+                validation.problems.push(InstallProblem::VersionMismatch {
+                    expected: installed.version.clone(),
+                    reported,
+                });
+            }
+        }
+        Err(failure) => {
+            validation.problems.push(InstallProblem::BinaryFailed {
+                binary: spec.binaries[0].to_string(),
+                message: failure.message,
+            });
+            validation.removable = failure.conclusive;
+            return validation;
+        }
+    }
+
+    if let Some(tool) = spec.support {
+        let tool_path = match tool.binary {
+            Some(name) => find_binary(&dirs, name),
+            None => Some(binary.clone()),
+        };
+        let result = match tool_path {
+            Some(tool_path) => probe(&tool_path, tool.args)
+                .await
+                .map(|_| ())
+                .map_err(|failure| failure.message),
+            None => Err("not found in the install".to_string()),
+        };
+        if let Err(message) = result {
+            validation.problems.push(InstallProblem::SupportToolBroken {
+                tool: tool.name.to_string(),
+                message,
+            });
+        }
+    }
+
+    validation
+}
+
+/// Validate the installed versions of `provider` a few at a time, keeping
+/// input order
+pub async fn validate_installs(
+    env_type: &str,
+    provider: &dyn EnvironmentProvider,
+    installed: &[InstalledVersion],
+) -> Vec<InstallValidation> {
+    stream::iter(installed.iter().map(|version| {
+        let bin_dirs = provider_bin_dirs(provider, &version.version);
+        async move { validate_install(env_type, version, bin_dirs.as_deref()).await }
+    }))
+    .buffered(MAX_CONCURRENT_PROBES)
+    .collect()
+    .await
+}
+
+/// Whether `install_path` looks like a per-version directory that is safe to
+/// delete outright, rather than a shared prefix such as `/usr`
+pub fn is_version_directory(install_path: &Path, version: &str) -> bool {
+    install_path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .is_some_and(|name| normalize_version(&name) == normalize_version(version))
+}
+
+/// Drop shims and `current` links that point at a removed install.
+/// Returns the names of the shims that were removed.
+pub async fn remove_version_pointers(
+    root_dir: &Path,
+    env_type: &str,
+    version: &str,
+    install_path: &Path,
+) -> CogniaResult<Vec<String>> {
+    let mut shims = ShimManager::new(root_dir).await?;
+    let owned: Vec<String> = shims
+        .list_shims()
+        .into_iter()
+        .filter(|config| {
+            config.env_type == env_type
+                && (config.version.as_deref() == Some(version)
+                    || config.target_path.starts_with(install_path))
+        })
+        .map(|config| config.binary_name.clone())
+        .collect();
+    for binary_name in &owned {
+        shims.remove_shim(binary_name).await?;
+    }
+
+    let version_dirs = [
+        install_path.parent().map(Path::to_path_buf),
+        Some(root_dir.join("versions").join(env_type)),
+    ];
+    for dir in version_dirs.into_iter().flatten() {
+        let link = dir.join("current");
+        let is_link = link
+            .symlink_metadata()
+            .map(|meta| meta.file_type().is_symlink())
+            .unwrap_or(false);
+        if !is_link {
+            continue;
+        }
+        let target = std::fs::read_link(&link).unwrap_or_default();
+        let target = if target.is_relative() {
+            dir.join(target)
+        } else {
+            target
+        };
+        if target.starts_with(install_path) {
+            std::fs::remove_file(&link).or_else(|_| std::fs::remove_dir(&link))?;
+        }
+    }
+
+    Ok(owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn installed(version: &str, path: &Path) -> InstalledVersion {
+        InstalledVersion {
+            version: version.to_string(),
+            install_path: path.to_path_buf(),
+            size: None,
+            installed_at: None,
+            is_current: false,
+        }
+    }
+
+    #[test]
+    fn test_version_matches_output() {
+        assert_eq!(version_matches_output("v20.11.0", "v20.11.0\n"), Some(true));
+        assert_eq!(
+            version_matches_output("3.12.1", "Python 3.12.1"),
+            Some(true)
+        );
+        assert_eq!(
+            version_matches_output("go1.22.0", "go version go1.22.0 linux/amd64"),
+            Some(true)
+        );
+        assert_eq!(
+            version_matches_output("21.0.2+13", "openjdk version \"21.0.2\" 2024-01-16"),
+            Some(true)
+        );
+        assert_eq!(version_matches_output("20.11.0", "v18.19.0"), Some(false));
+        assert_eq!(
+            version_matches_output("stable-x86_64-unknown-linux-gnu", "rustc 1.80.0"),
+            None
+        );
+        assert_eq!(version_matches_output("20.11.0", "garbage"), None);
+    }
+
+    #[test]
+    fn test_java_version_matches_output() {
+        let java8 = "java version \"1.8.0_392\"\nJava(TM) SE Runtime Environment";
+        assert_eq!(java_version_matches_output("8u392-b08", java8), Some(true));
+        assert_eq!(java_version_matches_output("1.8.0_392", java8), Some(true));
+        assert_eq!(java_version_matches_output("8.0.392+8", java8), Some(true));
+        assert_eq!(
+            java_version_matches_output("jdk-21.0.2+13", "openjdk version \"21.0.2\" 2024-01-16"),
+            Some(true)
+        );
+        assert_eq!(java_version_matches_output("17.0.9", java8), Some(false));
+    }
+
+    #[test]
+    fn test_is_version_directory() {
+        assert!(is_version_directory(Path::new("/v/node/v20.1.0"), "20.1.0"));
+        assert!(is_version_directory(
+            Path::new("/v/python/3.12.1"),
+            "3.12.1"
+        ));
+        assert!(!is_version_directory(Path::new("/usr"), "3.12.1"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_install_directory_problems() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing =
+            validate_install("node", &installed("20.0.0", &dir.path().join("nope")), None).await;
+        assert_eq!(missing.problems, vec![InstallProblem::MissingDirectory]);
+        assert!(missing.removable);
+
+        let empty_dir = dir.path().join("20.0.0");
+        std::fs::create_dir_all(&empty_dir).unwrap();
+        let empty = validate_install("node", &installed("20.0.0", &empty_dir), None).await;
+        assert_eq!(empty.problems, vec![InstallProblem::EmptyDirectory]);
+
+        std::fs::create_dir_all(empty_dir.join("lib")).unwrap();
+        let partial = validate_install("node", &installed("20.0.0", &empty_dir), None).await;
+        assert!(partial.is_broken());
+        assert_eq!(
+            partial.problems,
+            vec![InstallProblem::MissingBinary {
+                binary: "node".into()
+            }]
+        );
+        // Only a guessed location was searched, so the miss proves nothing
+        assert!(!partial.removable);
+
+        let bin_dirs = [empty_dir.join("bin")];
+        let confirmed =
+            validate_install("node", &installed("20.0.0", &empty_dir), Some(&bin_dirs)).await;
+        assert!(confirmed.is_broken());
+        assert!(confirmed.removable);
+
+        let unknown = validate_install("custom", &installed("1.0", &empty_dir), None).await;
+        assert!(!unknown.is_broken());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_validate_install_runs_binary_and_support_tool() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let bin = dir.path().join("20.1.0").join("bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        let write_script = |name: &str, body: &str| {
+            let path = bin.join(name);
+            std::fs::write(&path, format!("#!/bin/sh\n{}\n", body)).unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        };
+        write_script("node", "echo v20.1.0");
+        write_script("npm", "echo 'npm is broken' >&2; exit 1");
+
+        let install = installed("20.1.0", bin.parent().unwrap());
+        let result = validate_install("node", &install, None).await;
+        assert_eq!(
+            result.problems,
+            vec![InstallProblem::SupportToolBroken {
+                tool: "npm".into(),
+                message: "npm is broken".into()
+            }]
+        );
+        assert!(!result.removable);
+
+        write_script("npm", "echo 10.2.0");
+        write_script("node", "echo v18.0.0");
+        let result = validate_install("node", &install, None).await;
+        assert_eq!(
+            result.problems,
+            vec![InstallProblem::VersionMismatch {
+                expected: "20.1.0".into(),
+                reported: "18.0.0".into()
+            }]
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_validate_install_searches_provider_bin_dirs() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let install = dir.path().join("21.0.2");
+        let bin = install.join("Contents").join("Home").join("bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        let java = bin.join("java");
+        std::fs::write(
+            &java,
+            "#!/bin/sh\necho 'openjdk version \"21.0.2\" 2024-01-16' >&2\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&java, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let result =
+            validate_install("java", &installed("21.0.2", &install), Some(&[bin.clone()])).await;
+        assert!(!result.is_broken(), "{}", result.summary());
+
+        let guessed = validate_install("java", &installed("21.0.2", &install), None).await;
+        assert!(guessed.is_broken());
+        assert!(!guessed.removable);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_remove_version_pointers() {
+        use crate::core::shim::ShimConfig;
+
+        let root = tempfile::tempdir().unwrap();
+        let versions = root.path().join("versions").join("node");
+        let broken = versions.join("20.1.0");
+        let kept = versions.join("18.0.0");
+        std::fs::create_dir_all(&broken).unwrap();
+        std::fs::create_dir_all(&kept).unwrap();
+        std::os::unix::fs::symlink(&broken, versions.join("current")).unwrap();
+
+        let mut shims = ShimManager::new(root.path()).await.unwrap();
+        for (name, version, target) in [("node", "20.1.0", &broken), ("node18", "18.0.0", &kept)] {
+            shims
+                .create_shim(ShimConfig {
+                    env_type: "node".into(),
+                    binary_name: name.into(),
+                    version: Some(version.into()),
+                    target_path: target.join("bin").join("node"),
+                    direct: false,
+                    version_switch: false,
+                })
+                .await
+                .unwrap();
+        }
+
+        let removed = remove_version_pointers(root.path(), "node", "20.1.0", &broken)
+            .await
+            .unwrap();
+        assert_eq!(removed, vec!["node".to_string()]);
+        assert!(versions.join("current").symlink_metadata().is_err());
+
+        let shims = ShimManager::new(root.path()).await.unwrap();
+        let names: Vec<_> = shims
+            .list_shims()
+            .iter()
+            .map(|s| s.binary_name.clone())
+            .collect();
+        assert_eq!(names, vec!["node18".to_string()]);
+    }
+}
@@ -0,0 +1,613 @@
+//! Resource tracking for processes started through the launch commands.
+//!
+//! A launch is registered with its pid while the launch command awaits it. One
+//! sampler task refreshes process stats for every tracked launch at the
+//! configured interval, enforces per-launch memory limits and reports samples
+//! through the listener, which forwards them to the frontend.
+
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::process;
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessStatus, ProcessesToUpdate, Signal, System};
+
+/// How long `stop` waits for a graceful exit before killing the process tree
+pub const STOP_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+const DEFAULT_SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// What to do when a launch goes over its memory limit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitAction {
+    #[default]
+    Warn,
+    Kill,
+}
+
+/// Optional resource limits for a single launch
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchLimits {
+    /// Resident memory of the whole process tree, in bytes
+    pub max_memory_bytes: Option<u64>,
+    #[serde(default)]
+    pub on_exceed: LimitAction,
+}
+
+/// A process started through the launch commands that is still running
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackedLaunchInfo {
+    pub launch_id: String,
+    pub pid: u32,
+    pub program: String,
+    pub args: Vec<String>,
+    pub cwd: Option<String>,
+    /// Label of whatever started the launch, such as a project task
+    pub profile: Option<String>,
+    pub started_at: String,
+    pub limits: Option<LaunchLimits>,
+}
+
+/// Resource usage of a launch's process tree at one point in time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchStats {
+    pub launch_id: String,
+    pub pid: u32,
+    /// Summed over the process and its descendants, so it can exceed 100
+    /// on multi-core machines
+    pub cpu_percent: f32,
+    /// Resident set size of the process and its descendants, in bytes
+    pub memory_bytes: u64,
+    pub child_count: usize,
+    pub sampled_at: String,
+}
+
+/// Sent when a launch's process tree goes over its memory limit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchLimitEvent {
+    pub launch_id: String,
+    pub memory_bytes: u64,
+    pub max_memory_bytes: u64,
+    pub action: LimitAction,
+}
+
+#[derive(Debug, Clone)]
+pub enum LaunchMonitorEvent {
+    Started(TrackedLaunchInfo),
+    Sample(LaunchStats),
+    LimitExceeded(LaunchLimitEvent),
+    Exited { launch_id: String },
+}
+
+type LaunchListener = Arc<dyn Fn(LaunchMonitorEvent) + Send + Sync>;
+
+struct TrackedLaunch {
+    info: TrackedLaunchInfo,
+    last_sample: Option<LaunchStats>,
+    /// Set once the limit was reported, cleared when usage drops back under it
+    over_limit: bool,
+}
+
+/// One process as seen by a refresh, reduced to what tree accounting needs
+#[derive(Debug, Clone, Copy)]
+struct ProcessSnapshot {
+    pid: u32,
+    parent: Option<u32>,
+    cpu_percent: f32,
+    memory_bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TreeUsage {
+    cpu_percent: f32,
+    memory_bytes: u64,
+    child_count: usize,
+}
+
+/// Tracks running launches and samples their resource usage
+pub struct LaunchMonitor {
+    launches: Mutex<HashMap<String, TrackedLaunch>>,
+    system: Mutex<System>,
+    listener: std::sync::RwLock<Option<LaunchListener>>,
+    sample_interval_ms: AtomicU64,
+    sampler_running: AtomicBool,
+}
+
+pub type SharedLaunchMonitor = Arc<LaunchMonitor>;
+
+impl Default for LaunchMonitor {
+    fn default() -> Self {
+        Self {
+            launches: Mutex::new(HashMap::new()),
+            system: Mutex::new(System::new()),
+            listener: std::sync::RwLock::new(None),
+            sample_interval_ms: AtomicU64::new(DEFAULT_SAMPLE_INTERVAL.as_millis() as u64),
+            sampler_running: AtomicBool::new(false),
+        }
+    }
+}
+
+impl LaunchMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Receive lifecycle and sample events (e.g. to forward them to the frontend)
+    pub fn set_listener(&self, listener: impl Fn(LaunchMonitorEvent) + Send + Sync + 'static) {
+        if let Ok(mut guard) = self.listener.write() {
+            *guard = Some(Arc::new(listener));
+        }
+    }
+
+    fn emit(&self, event: LaunchMonitorEvent) {
+        let listener = self.listener.read().ok().and_then(|guard| guard.clone());
+        if let Some(listener) = listener {
+            listener(event);
+        }
+    }
+
+    fn launches(&self) -> std::sync::MutexGuard<'_, HashMap<String, TrackedLaunch>> {
+        self.launches.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn set_sample_interval(&self, interval: Duration) {
+        let millis = interval
+            .max(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL)
+            .as_millis() as u64;
+        self.sample_interval_ms.store(millis, Ordering::Relaxed);
+    }
+
+    fn sample_interval(&self) -> Duration {
+        Duration::from_millis(self.sample_interval_ms.load(Ordering::Relaxed))
+    }
+
+    /// Start tracking a launch and make sure the sampler is running.
+    /// Must be called from within a Tokio runtime.
+    pub fn track(self: &Arc<Self>, info: TrackedLaunchInfo) {
+        debug!("Tracking launch {} (pid {})", info.launch_id, info.pid);
+        self.launches().insert(
+            info.launch_id.clone(),
+            TrackedLaunch {
+                info: info.clone(),
+                last_sample: None,
+                over_limit: false,
+            },
+        );
+        self.emit(LaunchMonitorEvent::Started(info));
+
+        if !self.sampler_running.swap(true, Ordering::SeqCst) {
+            let monitor = self.clone();
+            tokio::spawn(async move { monitor.run_sampler().await });
+        }
+    }
+
+    /// Stop tracking a launch. Returns whether it was tracked.
+    pub fn untrack(&self, launch_id: &str) -> bool {
+        let removed = self.launches().remove(launch_id).is_some();
+        if removed {
+            self.emit(LaunchMonitorEvent::Exited {
+                launch_id: launch_id.to_string(),
+            });
+        }
+        removed
+    }
+
+    /// Running launches, oldest first
+    pub fn list(&self) -> Vec<TrackedLaunchInfo> {
+        let mut infos: Vec<_> = self.launches().values().map(|l| l.info.clone()).collect();
+        infos.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+        infos
+    }
+
+    fn pid_of(&self, launch_id: &str) -> CogniaResult<u32> {
+        self.launches()
+            .get(launch_id)
+            .map(|launch| launch.info.pid)
+            .ok_or_else(|| launch_not_found(launch_id))
+    }
+
+    /// Run `f` on the blocking pool: a sysinfo refresh reads every process
+    async fn blocking<T: Send + 'static>(
+        self: &Arc<Self>,
+        f: impl FnOnce(&Self) -> T + Send + 'static,
+    ) -> CogniaResult<T> {
+        let monitor = self.clone();
+        tokio::task::spawn_blocking(move || f(&monitor))
+            .await
+            .map_err(|e| CogniaError::Internal(e.to_string()))
+    }
+
+    /// Latest sample for a launch, taking one on the spot if the sampler has
+    /// not reached it yet.
+    pub async fn stats(self: &Arc<Self>, launch_id: &str) -> CogniaResult<LaunchStats> {
+        let cached = self
+            .launches()
+            .get(launch_id)
+            .ok_or_else(|| launch_not_found(launch_id))?
+            .last_sample
+            .clone();
+        if let Some(stats) = cached {
+            return Ok(stats);
+        }
+
+        // CPU usage is measured between two refreshes
+        self.blocking(|monitor| monitor.refresh_all()).await?;
+        tokio::time::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL).await;
+        let snapshots = self.blocking(|monitor| monitor.refresh_all()).await?;
+        let pid = self.pid_of(launch_id)?;
+        let usage = tree_usage(pid, &snapshots).ok_or_else(|| {
+            CogniaError::Internal(format!(
+                "Process {} of launch '{}' has exited",
+                pid, launch_id
+            ))
+        })?;
+        Ok(stats_for(launch_id, pid, usage))
+    }
+
+    /// Stop a launch. Unless `force` is set, the process is first asked to
+    /// exit and gets [`STOP_GRACE_PERIOD`] to do so before its whole tree is
+    /// killed. Returns whether it exited without being killed.
+    pub async fn stop(self: &Arc<Self>, launch_id: &str, force: bool) -> CogniaResult<bool> {
+        let pid = self.pid_of(launch_id)?;
+        if !self.blocking(move |monitor| monitor.is_alive(pid)).await? {
+            return Ok(true);
+        }
+
+        if !force && self.request_exit(pid).await {
+            let deadline = Instant::now() + STOP_GRACE_PERIOD;
+            while Instant::now() < deadline {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                if !self.blocking(move |monitor| monitor.is_alive(pid)).await? {
+                    return Ok(true);
+                }
+            }
+            debug!("Launch {} ignored the exit request, killing it", launch_id);
+        }
+
+        self.blocking(move |monitor| monitor.kill_tree(pid)).await?;
+        Ok(false)
+    }
+
+    /// Kill every tracked launch. Called on app shutdown when launches
+    /// should not outlive the app.
+    pub fn terminate_all(&self) -> usize {
+        let pids: Vec<u32> = self.launches().drain().map(|(_, l)| l.info.pid).collect();
+        for pid in &pids {
+            self.kill_tree(*pid);
+        }
+        if !pids.is_empty() {
+            warn!("Terminated {} launched process(es)", pids.len());
+        }
+        pids.len()
+    }
+
+    /// Forget every tracked launch and leave the processes running
+    pub fn untrack_all(&self) -> usize {
+        let mut launches = self.launches();
+        let count = launches.len();
+        launches.clear();
+        count
+    }
+
+    async fn run_sampler(self: Arc<Self>) {
+        loop {
+            tokio::time::sleep(self.sample_interval()).await;
+            let monitor = self.clone();
+            let active = tokio::task::spawn_blocking(move || monitor.sample_all())
+                .await
+                .unwrap_or(false);
+            if active {
+                continue;
+            }
+            self.sampler_running.store(false, Ordering::SeqCst);
+            // A launch tracked in the meantime may have seen the flag still set
+            if self.launches().is_empty() || self.sampler_running.swap(true, Ordering::SeqCst) {
+                break;
+            }
+        }
+    }
+
+    /// Sample every tracked launch and enforce limits. Returns whether any
+    /// launch is still tracked.
+    fn sample_all(&self) -> bool {
+        if self.launches().is_empty() {
+            return false;
+        }
+        let snapshots = self.refresh_all();
+
+        let mut events = Vec::new();
+        let mut to_kill = Vec::new();
+        {
+            let mut launches = self.launches();
+            for (launch_id, launch) in launches.iter_mut() {
+                let Some(usage) = tree_usage(launch.info.pid, &snapshots) else {
+                    continue;
+                };
+                let stats = stats_for(launch_id, launch.info.pid, usage);
+                launch.last_sample = Some(stats.clone());
+                events.push(LaunchMonitorEvent::Sample(stats));
+
+                let Some(limits) = &launch.info.limits else {
+                    continue;
+                };
+                match limit_breach(limits, usage.memory_bytes) {
+                    Some(max_memory_bytes) if !launch.over_limit => {
+                        launch.over_limit = true;
+                        events.push(LaunchMonitorEvent::LimitExceeded(LaunchLimitEvent {
+                            launch_id: launch_id.clone(),
+                            memory_bytes: usage.memory_bytes,
+                            max_memory_bytes,
+                            action: limits.on_exceed,
+                        }));
+                        if limits.on_exceed == LimitAction::Kill {
+                            to_kill.push(launch.info.pid);
+                        }
+                    }
+                    Some(_) => {}
+                    None => launch.over_limit = false,
+                }
+            }
+        }
+
+        for event in events {
+            self.emit(event);
+        }
+        for pid in to_kill {
+            warn!("Killing launched process {} over its memory limit", pid);
+            self.kill_tree(pid);
+        }
+        true
+    }
+
+    fn refresh_all(&self) -> Vec<ProcessSnapshot> {
+        let mut system = self.system.lock().unwrap_or_else(|e| e.into_inner());
+        system.refresh_processes(ProcessesToUpdate::All, true);
+        system
+            .processes()
+            .values()
+            // Linux lists threads as processes of their own
+            .filter(|p| p.thread_kind().is_none() && p.status() != ProcessStatus::Zombie)
+            .map(|p| ProcessSnapshot {
+                pid: p.pid().as_u32(),
+                parent: p.parent().map(|parent| parent.as_u32()),
+                cpu_percent: p.cpu_usage(),
+                memory_bytes: p.memory(),
+            })
+            .collect()
+    }
+
+    fn is_alive(&self, pid: u32) -> bool {
+        let pid = Pid::from_u32(pid);
+        let mut system = self.system.lock().unwrap_or_else(|e| e.into_inner());
+        system.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+        system
+            .process(pid)
+            .is_some_and(|p| p.status() != ProcessStatus::Zombie)
+    }
+
+    /// Ask a process to exit: SIGTERM on Unix. Windows has no such signal for
+    /// console programs, which a plain `taskkill` cannot close, so the tree is
+    /// ended with `taskkill /F` right away. Returns whether that went through.
+    async fn request_exit(&self, pid: u32) -> bool {
+        let signalled = {
+            let system = self.system.lock().unwrap_or_else(|e| e.into_inner());
+            system
+                .process(Pid::from_u32(pid))
+                .and_then(|p| p.kill_with(Signal::Term))
+        };
+        match signalled {
+            Some(sent) => sent,
+            None => {
+                let pid = pid.to_string();
+                process::execute("taskkill", &["/PID", &pid, "/T", "/F"], None)
+                    .await
+                    .is_ok_and(|out| out.success)
+            }
+        }
+    }
+
+    /// Kill a process and all of its descendants, deepest first
+    fn kill_tree(&self, pid: u32) {
+        let snapshots = self.refresh_all();
+        let mut pids = descendants(pid, &snapshots);
+        pids.reverse();
+        pids.push(pid);
+
+        let system = self.system.lock().unwrap_or_else(|e| e.into_inner());
+        for pid in pids {
+            if let Some(process) = system.process(Pid::from_u32(pid)) {
+                if !process.kill() {
+                    debug!("Failed to kill process {}", pid);
+                }
+            }
+        }
+    }
+}
+
+fn launch_not_found(launch_id: &str) -> CogniaError {
+    CogniaError::NotFound(format!("launch '{}' is not running", launch_id))
+}
+
+fn stats_for(launch_id: &str, pid: u32, usage: TreeUsage) -> LaunchStats {
+    LaunchStats {
+        launch_id: launch_id.to_string(),
+        pid,
+        cpu_percent: usage.cpu_percent,
+        memory_bytes: usage.memory_bytes,
+        child_count: usage.child_count,
+        sampled_at: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
+/// Descendants of `root`, parents before their children
+fn descendants(root: u32, snapshots: &[ProcessSnapshot]) -> Vec<u32> {
+    let mut found = vec![root];
+    let mut next = 0;
+    while next < found.len() {
+        let parent = found[next];
+        found.extend(
+            snapshots
+                .iter()
+                .filter(|p| p.parent == Some(parent) && p.pid != parent)
+                .map(|p| p.pid),
+        );
+        next += 1;
+    }
+    found.remove(0);
+    found
+}
+
+/// Usage of `root` plus its descendants, or `None` if `root` is gone
+fn tree_usage(root: u32, snapshots: &[ProcessSnapshot]) -> Option<TreeUsage> {
+    let by_pid: HashMap<u32, &ProcessSnapshot> = snapshots.iter().map(|p| (p.pid, p)).collect();
+    let root_snapshot = by_pid.get(&root)?;
+    let children = descendants(root, snapshots);
+
+    let mut usage = TreeUsage {
+        cpu_percent: root_snapshot.cpu_percent,
+        memory_bytes: root_snapshot.memory_bytes,
+        child_count: children.len(),
+    };
+    for pid in children {
+        if let Some(child) = by_pid.get(&pid) {
+            usage.cpu_percent += child.cpu_percent;
+            usage.memory_bytes += child.memory_bytes;
+        }
+    }
+    Some(usage)
+}
+
+/// The memory limit `memory_bytes` is over, if any
+fn limit_breach(limits: &LaunchLimits, memory_bytes: u64) -> Option<u64> {
+    limits.max_memory_bytes.filter(|max| memory_bytes > *max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(
+        pid: u32,
+        parent: Option<u32>,
+        cpu_percent: f32,
+        memory_bytes: u64,
+    ) -> ProcessSnapshot {
+        ProcessSnapshot {
+            pid,
+            parent,
+            cpu_percent,
+            memory_bytes,
+        }
+    }
+
+    fn info(launch_id: &str, pid: u32) -> TrackedLaunchInfo {
+        TrackedLaunchInfo {
+            launch_id: launch_id.into(),
+            pid,
+            program: "node".into(),
+            args: vec!["server.js".into()],
+            cwd: None,
+            profile: None,
+            started_at: chrono::Utc::now().to_rfc3339(),
+            limits: None,
+        }
+    }
+
+    #[test]
+    fn test_tree_usage_sums_descendants() {
+        let snapshots = vec![
+            snapshot(1, None, 1.0, 100),
+            snapshot(10, Some(1), 5.0, 1_000),
+            snapshot(11, Some(10), 20.0, 4_000),
+            snapshot(12, Some(10), 2.5, 500),
+            snapshot(20, Some(1), 50.0, 9_999),
+        ];
+        let usage = tree_usage(10, &snapshots).unwrap();
+        assert_eq!(
+            usage,
+            TreeUsage {
+                cpu_percent: 27.5,
+                memory_bytes: 5_500,
+                child_count: 2,
+            }
+        );
+        assert!(tree_usage(99, &snapshots).is_none());
+    }
+
+    #[test]
+    fn test_descendants_orders_parents_first() {
+        let snapshots = vec![
+            snapshot(30, Some(21), 0.0, 0),
+            snapshot(21, Some(10), 0.0, 0),
+            snapshot(10, None, 0.0, 0),
+            snapshot(22, Some(10), 0.0, 0),
+        ];
+        assert_eq!(descendants(10, &snapshots), vec![21, 22, 30]);
+        assert!(descendants(30, &snapshots).is_empty());
+    }
+
+    #[test]
+    fn test_limit_breach() {
+        let limits = LaunchLimits {
+            max_memory_bytes: Some(1_024),
+            on_exceed: LimitAction::Kill,
+        };
+        assert_eq!(limit_breach(&limits, 2_048), Some(1_024));
+        assert_eq!(limit_breach(&limits, 1_024), None);
+        assert_eq!(limit_breach(&LaunchLimits::default(), u64::MAX), None);
+    }
+
+    #[test]
+    fn test_limits_deserialize_with_default_action() {
+        let limits: LaunchLimits = serde_json::from_str(r#"{"maxMemoryBytes":512}"#).unwrap();
+        assert_eq!(limits.max_memory_bytes, Some(512));
+        assert_eq!(limits.on_exceed, LimitAction::Warn);
+    }
+
+    #[tokio::test]
+    async fn test_track_untrack_and_missing_launch() {
+        let monitor = Arc::new(LaunchMonitor::new());
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        monitor.set_listener(move |event| {
+            let name = match event {
+                LaunchMonitorEvent::Started(_) => "started",
+                LaunchMonitorEvent::Sample(_) => "sample",
+                LaunchMonitorEvent::LimitExceeded(_) => "limit",
+                LaunchMonitorEvent::Exited { .. } => "exited",
+            };
+            sink.lock().unwrap().push(name);
+        });
+
+        monitor.track(info("a", std::process::id()));
+        assert_eq!(monitor.list().len(), 1);
+        assert!(monitor.untrack("a"));
+        assert!(!monitor.untrack("a"));
+        assert_eq!(*events.lock().unwrap(), vec!["started", "exited"]);
+
+        assert!(matches!(
+            monitor.stats("missing").await,
+            Err(CogniaError::NotFound(_))
+        ));
+        assert!(monitor.stop("missing", true).await.is_err());
+        assert_eq!(monitor.untrack_all(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_stats_samples_running_process() {
+        let monitor = Arc::new(LaunchMonitor::new());
+        monitor.track(info("self", std::process::id()));
+        let stats = monitor.stats("self").await.unwrap();
+        assert_eq!(stats.pid, std::process::id());
+        assert!(stats.memory_bytes > 0);
+        assert_eq!(monitor.untrack_all(), 1);
+    }
+}
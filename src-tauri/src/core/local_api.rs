@@ -0,0 +1,837 @@
+//! Read-only JSON-RPC 2.0 API for editors and CI scripts.
+//!
+//! The server listens on a unix socket under the cognia root (a named pipe on
+//! Windows) and speaks newline-delimited JSON-RPC. A connection must first call
+//! `auth` with the token from `<root>/api/token`; that file is rewritten on
+//! every start and is readable by the current user only. Each method has its
+//! own per-minute call budget shared by all connections, while failed `auth`
+//! calls are counted per connection. The actual methods are dispatched by the
+//! caller, so this module only knows their names.
+
+use crate::error::{CogniaError, CogniaResult};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+
+/// Methods served after authentication. None of them change state.
+pub const LOCAL_API_METHODS: &[&str] = &["env.detect", "env.list", "project.pins", "shims.list"];
+
+/// Calls per method per minute when the setting is left at its default
+pub const DEFAULT_RATE_LIMIT_PER_MINUTE: u32 = 120;
+
+const AUTH_METHOD: &str = "auth";
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+const MAX_MESSAGE_BYTES: u64 = 64 * 1024;
+/// Failed `auth` calls after which a connection is closed
+const MAX_AUTH_FAILURES: u32 = 5;
+
+/// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+const UNAUTHORIZED: i64 = -32001;
+const RATE_LIMITED: i64 = -32002;
+
+static SERVER: Lazy<StdMutex<Option<RunningServer>>> = Lazy::new(|| StdMutex::new(None));
+static LAST_ERROR: Lazy<StdMutex<Option<String>>> = Lazy::new(|| StdMutex::new(None));
+
+struct RunningServer {
+    root_dir: PathBuf,
+    endpoint: String,
+    rate_limit_per_minute: u32,
+    started_at: String,
+    handle: tauri::async_runtime::JoinHandle<()>,
+}
+
+/// What the settings UI shows about the server
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalApiStatus {
+    pub running: bool,
+    /// Socket path or pipe name clients connect to
+    pub endpoint: Option<String>,
+    /// File holding the token clients pass to `auth`
+    pub token_path: Option<String>,
+    pub methods: Vec<String>,
+    pub rate_limit_per_minute: Option<u32>,
+    pub started_at: Option<String>,
+    /// Why the last start attempt failed
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(INVALID_PARAMS, message)
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(INTERNAL_ERROR, message)
+    }
+}
+
+// ── Endpoint and token ──
+
+pub fn token_path(root_dir: &Path) -> PathBuf {
+    root_dir.join("api").join("token")
+}
+
+#[cfg(unix)]
+pub fn endpoint_for(root_dir: &Path) -> String {
+    root_dir
+        .join("api")
+        .join("cognia.sock")
+        .display()
+        .to_string()
+}
+
+/// Named pipes live in a machine-wide namespace, so the pipe name carries
+/// the user name to keep two signed-in users apart
+#[cfg(windows)]
+pub fn endpoint_for(_root_dir: &Path) -> String {
+    let user: String = std::env::var("USERNAME")
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    format!(r"\\.\pipe\cognia-api-{}", user)
+}
+
+fn generate_token() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+/// Write a fresh token readable only by the current user. On Windows the
+/// file inherits the user-only ACL of the profile directory it lives in.
+fn write_token(path: &Path) -> CogniaResult<String> {
+    use std::io::Write;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    // Replace rather than truncate so an existing file's looser mode is not kept
+    let _ = std::fs::remove_file(path);
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let token = generate_token();
+    let mut file = options.open(path)?;
+    file.write_all(token.as_bytes())?;
+    Ok(token)
+}
+
+fn tokens_match(expected: &str, given: &str) -> bool {
+    expected.len() == given.len()
+        && expected
+            .bytes()
+            .zip(given.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+// ── Rate limiting ──
+
+/// Fixed one-minute window per method
+struct RateLimiter {
+    limit: u32,
+    windows: StdMutex<HashMap<String, (Instant, u32)>>,
+}
+
+impl RateLimiter {
+    fn new(limit: u32) -> Self {
+        Self {
+            limit,
+            windows: StdMutex::new(HashMap::new()),
+        }
+    }
+
+    /// Count a call, or return the seconds until the method is allowed again
+    fn check(&self, method: &str, now: Instant) -> Result<(), u64> {
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        let (started, count) = windows.entry(method.to_string()).or_insert((now, 0));
+        if now.duration_since(*started) >= RATE_LIMIT_WINDOW {
+            *started = now;
+            *count = 0;
+        }
+        if *count >= self.limit {
+            let retry_after = RATE_LIMIT_WINDOW.saturating_sub(now.duration_since(*started));
+            return Err(retry_after.as_secs().max(1));
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
+// ── Protocol ──
+
+/// State of one client connection
+#[derive(Debug, Default)]
+struct Session {
+    authenticated: bool,
+    auth_failures: u32,
+}
+
+impl Session {
+    fn locked_out(&self) -> bool {
+        self.auth_failures >= MAX_AUTH_FAILURES
+    }
+}
+
+struct ServerContext<F> {
+    token: String,
+    limiter: RateLimiter,
+    dispatch: F,
+}
+
+fn response(id: Value, outcome: Result<Value, RpcError>) -> Value {
+    match outcome {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({ "jsonrpc": "2.0", "id": id, "error": error }),
+    }
+}
+
+/// Handle one JSON-RPC message. Returns `None` for notifications.
+async fn handle_message<F, Fut>(
+    raw: &str,
+    session: &mut Session,
+    ctx: &ServerContext<F>,
+) -> Option<Value>
+where
+    F: Fn(&'static str, Value) -> Fut,
+    Fut: Future<Output = Result<Value, RpcError>>,
+{
+    let message: Value = match serde_json::from_str(raw) {
+        Ok(message) => message,
+        Err(e) => {
+            return Some(response(
+                Value::Null,
+                Err(RpcError::new(PARSE_ERROR, format!("Parse error: {}", e))),
+            ))
+        }
+    };
+    let Some(request) = message.as_object() else {
+        let reason = if message.is_array() {
+            "Batch requests are not supported"
+        } else {
+            "Request must be an object"
+        };
+        return Some(response(
+            Value::Null,
+            Err(RpcError::new(INVALID_REQUEST, reason)),
+        ));
+    };
+
+    let id = request.get("id").cloned();
+    let method = request.get("method").and_then(Value::as_str);
+    let (Some("2.0"), Some(method)) = (request.get("jsonrpc").and_then(Value::as_str), method)
+    else {
+        return Some(response(
+            id.unwrap_or(Value::Null),
+            Err(RpcError::new(
+                INVALID_REQUEST,
+                "Expected jsonrpc \"2.0\" and a method",
+            )),
+        ));
+    };
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+    let outcome = handle_call(method, params, session, ctx).await;
+    id.map(|id| response(id, outcome))
+}
+
+async fn handle_call<F, Fut>(
+    method: &str,
+    params: Value,
+    session: &mut Session,
+    ctx: &ServerContext<F>,
+) -> Result<Value, RpcError>
+where
+    F: Fn(&'static str, Value) -> Fut,
+    Fut: Future<Output = Result<Value, RpcError>>,
+{
+    // Resolve to the static name so unknown strings never reach the limiter
+    let known = std::iter::once(AUTH_METHOD)
+        .chain(LOCAL_API_METHODS.iter().copied())
+        .find(|m| *m == method);
+    let Some(method) = known else {
+        return Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Method not found: {}", method),
+        ));
+    };
+
+    // Failed attempts count against this connection only: a shared budget
+    // would let one client lock every other one out
+    if method == AUTH_METHOD {
+        if session.locked_out() {
+            return Err(RpcError::new(UNAUTHORIZED, "Too many failed auth attempts"));
+        }
+        let given = params.get("token").and_then(Value::as_str).unwrap_or("");
+        if !tokens_match(&ctx.token, given) {
+            session.auth_failures += 1;
+            return Err(RpcError::new(UNAUTHORIZED, "Invalid token"));
+        }
+        session.authenticated = true;
+        return Ok(json!({ "authenticated": true, "methods": LOCAL_API_METHODS }));
+    }
+
+    // Unauthenticated calls must not eat into the budget of real clients
+    if !session.authenticated {
+        return Err(RpcError::new(
+            UNAUTHORIZED,
+            "Call auth with the API token first",
+        ));
+    }
+    if let Err(retry_after) = ctx.limiter.check(method, Instant::now()) {
+        return Err(RpcError {
+            code: RATE_LIMITED,
+            message: format!("Rate limit exceeded for {}", method),
+            data: Some(json!({ "retryAfterSecs": retry_after })),
+        });
+    }
+
+    (ctx.dispatch)(method, params).await
+}
+
+async fn handle_connection<S, F, Fut>(stream: S, ctx: Arc<ServerContext<F>>)
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+    F: Fn(&'static str, Value) -> Fut,
+    Fut: Future<Output = Result<Value, RpcError>>,
+{
+    let (read_half, mut write_half) = tokio::io::split(stream);
+    let mut reader = BufReader::new(read_half);
+    let mut session = Session::default();
+    let mut buf = Vec::new();
+
+    loop {
+        buf.clear();
+        let read = match (&mut reader)
+            .take(MAX_MESSAGE_BYTES + 1)
+            .read_until(b'\n', &mut buf)
+            .await
+        {
+            Ok(0) | Err(_) => return,
+            Ok(read) => read,
+        };
+        if read as u64 > MAX_MESSAGE_BYTES {
+            let reply = response(
+                Value::Null,
+                Err(RpcError::new(INVALID_REQUEST, "Message too large")),
+            );
+            let _ = write_half
+                .write_all(format!("{}\n", reply).as_bytes())
+                .await;
+            return;
+        }
+
+        let line = String::from_utf8_lossy(&buf);
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(reply) = handle_message(line.trim(), &mut session, &ctx).await {
+            if write_half
+                .write_all(format!("{}\n", reply).as_bytes())
+                .await
+                .is_err()
+            {
+                return;
+            }
+        }
+        if session.locked_out() {
+            return;
+        }
+    }
+}
+
+// ── Transports ──
+
+#[cfg(unix)]
+async fn serve<F, Fut>(
+    endpoint: String,
+    ctx: Arc<ServerContext<F>>,
+) -> CogniaResult<impl Future<Output = ()>>
+where
+    F: Fn(&'static str, Value) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<Value, RpcError>> + Send + 'static,
+{
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = PathBuf::from(&endpoint);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    // A socket left behind by a crash would make bind fail
+    let _ = std::fs::remove_file(&path);
+    let listener = tokio::net::UnixListener::bind(&path)
+        .map_err(|e| CogniaError::Internal(format!("Failed to bind {}: {}", endpoint, e)))?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+
+    Ok(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    tokio::spawn(handle_connection(stream, ctx.clone()));
+                }
+                Err(e) => log::debug!("Local API accept failed: {}", e),
+            }
+        }
+    })
+}
+
+#[cfg(windows)]
+async fn serve<F, Fut>(
+    endpoint: String,
+    ctx: Arc<ServerContext<F>>,
+) -> CogniaResult<impl Future<Output = ()>>
+where
+    F: Fn(&'static str, Value) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<Value, RpcError>> + Send + 'static,
+{
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    // `first_pipe_instance` fails if another process already owns the name
+    let first = create_user_only_pipe(
+        ServerOptions::new()
+            .first_pipe_instance(true)
+            .reject_remote_clients(true),
+        &endpoint,
+    )
+    .map_err(|e| CogniaError::Internal(format!("Failed to create {}: {}", endpoint, e)))?;
+
+    Ok(async move {
+        let mut server = first;
+        loop {
+            let connected = server.connect().await;
+            let next =
+                create_user_only_pipe(ServerOptions::new().reject_remote_clients(true), &endpoint);
+            let next = match next {
+                Ok(next) => next,
+                Err(e) => {
+                    log::warn!("Local API stopped: failed to create pipe instance: {}", e);
+                    return;
+                }
+            };
+            let previous = std::mem::replace(&mut server, next);
+            match connected {
+                Ok(()) => {
+                    tokio::spawn(handle_connection(previous, ctx.clone()));
+                }
+                Err(e) => log::debug!("Local API connect failed: {}", e),
+            }
+        }
+    })
+}
+
+/// Create a pipe instance only the current user can open. The default DACL
+/// also grants read access to Everyone and the anonymous account.
+#[cfg(windows)]
+fn create_user_only_pipe(
+    options: &tokio::net::windows::named_pipe::ServerOptions,
+    endpoint: &str,
+) -> std::io::Result<tokio::net::windows::named_pipe::NamedPipeServer> {
+    use std::ffi::c_void;
+    use std::io::Error;
+    use std::mem::size_of;
+    use windows_sys::Win32::Foundation::HANDLE;
+    use windows_sys::Win32::Security::{
+        AddAccessAllowedAce, GetLengthSid, GetTokenInformation, InitializeAcl,
+        InitializeSecurityDescriptor, SetSecurityDescriptorDacl, TokenUser, ACCESS_ALLOWED_ACE,
+        ACL, ACL_REVISION, SECURITY_ATTRIBUTES, SECURITY_DESCRIPTOR, TOKEN_USER,
+    };
+    use windows_sys::Win32::Storage::FileSystem::FILE_ALL_ACCESS;
+
+    /// `GetCurrentProcessToken()`, which the Windows headers define inline
+    const CURRENT_PROCESS_TOKEN: HANDLE = -4isize as HANDLE;
+    const SECURITY_DESCRIPTOR_REVISION: u32 = 1;
+
+    // SAFETY: the token, ACL and descriptor buffers outlive the create call,
+    // and the u64 buffers are aligned for the structures written into them.
+    unsafe {
+        let mut len = 0u32;
+        GetTokenInformation(
+            CURRENT_PROCESS_TOKEN,
+            TokenUser,
+            std::ptr::null_mut(),
+            0,
+            &mut len,
+        );
+        let mut token_user = vec![0u64; (len as usize).div_ceil(8)];
+        if GetTokenInformation(
+            CURRENT_PROCESS_TOKEN,
+            TokenUser,
+            token_user.as_mut_ptr().cast(),
+            len,
+            &mut len,
+        ) == 0
+        {
+            return Err(Error::last_os_error());
+        }
+        let sid = (*token_user.as_ptr().cast::<TOKEN_USER>()).User.Sid;
+
+        // The ACE's `SidStart` field is the first 4 bytes of the SID
+        let acl_len = size_of::<ACL>() + size_of::<ACCESS_ALLOWED_ACE>() - size_of::<u32>()
+            + GetLengthSid(sid) as usize;
+        let mut acl = vec![0u64; acl_len.div_ceil(8)];
+        let acl_ptr = acl.as_mut_ptr().cast::<ACL>();
+        if InitializeAcl(acl_ptr, acl_len as u32, ACL_REVISION) == 0
+            || AddAccessAllowedAce(acl_ptr, ACL_REVISION, FILE_ALL_ACCESS, sid) == 0
+        {
+            return Err(Error::last_os_error());
+        }
+
+        let mut descriptor: SECURITY_DESCRIPTOR = std::mem::zeroed();
+        let descriptor_ptr = (&mut descriptor as *mut SECURITY_DESCRIPTOR).cast::<c_void>();
+        if InitializeSecurityDescriptor(descriptor_ptr, SECURITY_DESCRIPTOR_REVISION) == 0
+            || SetSecurityDescriptorDacl(descriptor_ptr, 1, acl_ptr, 0) == 0
+        {
+            return Err(Error::last_os_error());
+        }
+        let mut attributes = SECURITY_ATTRIBUTES {
+            nLength: size_of::<SECURITY_ATTRIBUTES>() as u32,
+            lpSecurityDescriptor: descriptor_ptr,
+            bInheritHandle: 0,
+        };
+        options.create_with_security_attributes_raw(
+            endpoint,
+            (&mut attributes as *mut SECURITY_ATTRIBUTES).cast::<c_void>(),
+        )
+    }
+}
+
+// ── Lifecycle ──
+
+fn stop_running(running: &mut Option<RunningServer>) {
+    if let Some(previous) = running.take() {
+        previous.handle.abort();
+        #[cfg(unix)]
+        let _ = std::fs::remove_file(&previous.endpoint);
+        let _ = std::fs::remove_file(token_path(&previous.root_dir));
+        log::info!("Local API on {} stopped", previous.endpoint);
+    }
+}
+
+fn set_last_error(error: Option<String>) {
+    *LAST_ERROR.lock().unwrap_or_else(|e| e.into_inner()) = error;
+}
+
+/// Start, restart or stop the server so it matches the given settings.
+/// Re-applying the current state is a no-op and keeps the token.
+pub async fn apply_server<F, Fut>(
+    enabled: bool,
+    root_dir: &Path,
+    rate_limit_per_minute: u32,
+    dispatch: F,
+) -> CogniaResult<()>
+where
+    F: Fn(&'static str, Value) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<Value, RpcError>> + Send + 'static,
+{
+    {
+        let mut running = SERVER.lock().unwrap_or_else(|e| e.into_inner());
+        let unchanged = running.as_ref().is_some_and(|s| {
+            s.root_dir == root_dir && s.rate_limit_per_minute == rate_limit_per_minute
+        });
+        if enabled && unchanged {
+            return Ok(());
+        }
+        stop_running(&mut running);
+    }
+    set_last_error(None);
+
+    if !enabled {
+        return Ok(());
+    }
+
+    let result = start(root_dir, rate_limit_per_minute, dispatch).await;
+    if let Err(e) = &result {
+        set_last_error(Some(e.to_string()));
+    }
+    result
+}
+
+async fn start<F, Fut>(root_dir: &Path, rate_limit_per_minute: u32, dispatch: F) -> CogniaResult<()>
+where
+    F: Fn(&'static str, Value) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<Value, RpcError>> + Send + 'static,
+{
+    let token = write_token(&token_path(root_dir))?;
+    let endpoint = endpoint_for(root_dir);
+    let ctx = Arc::new(ServerContext {
+        token,
+        limiter: RateLimiter::new(rate_limit_per_minute),
+        dispatch,
+    });
+
+    let accept_loop = match serve(endpoint.clone(), ctx).await {
+        Ok(accept_loop) => accept_loop,
+        Err(e) => {
+            let _ = std::fs::remove_file(token_path(root_dir));
+            return Err(e);
+        }
+    };
+    let handle = tauri::async_runtime::spawn(accept_loop);
+    log::info!("Local API listening on {}", endpoint);
+
+    let mut running = SERVER.lock().unwrap_or_else(|e| e.into_inner());
+    stop_running(&mut running);
+    *running = Some(RunningServer {
+        root_dir: root_dir.to_path_buf(),
+        endpoint,
+        rate_limit_per_minute,
+        started_at: chrono::Utc::now().to_rfc3339(),
+        handle,
+    });
+    Ok(())
+}
+
+pub fn status() -> LocalApiStatus {
+    let running = SERVER.lock().unwrap_or_else(|e| e.into_inner());
+    let last_error = LAST_ERROR.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    LocalApiStatus {
+        running: running.is_some(),
+        endpoint: running.as_ref().map(|s| s.endpoint.clone()),
+        token_path: running
+            .as_ref()
+            .map(|s| token_path(&s.root_dir).display().to_string()),
+        methods: LOCAL_API_METHODS.iter().map(|m| m.to_string()).collect(),
+        rate_limit_per_minute: running.as_ref().map(|s| s.rate_limit_per_minute),
+        started_at: running.as_ref().map(|s| s.started_at.clone()),
+        last_error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(
+        limit: u32,
+    ) -> ServerContext<impl Fn(&'static str, Value) -> std::future::Ready<Result<Value, RpcError>>>
+    {
+        ServerContext {
+            token: "secret".to_string(),
+            limiter: RateLimiter::new(limit),
+            dispatch: |method: &'static str, params: Value| {
+                std::future::ready(match method {
+                    "shims.list" => Ok(json!([])),
+                    _ => Ok(json!({ "method": method, "params": params })),
+                })
+            },
+        }
+    }
+
+    async fn call(
+        ctx: &ServerContext<
+            impl Fn(&'static str, Value) -> std::future::Ready<Result<Value, RpcError>>,
+        >,
+        session: &mut Session,
+        raw: &str,
+    ) -> Value {
+        handle_message(raw, session, ctx).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_methods_require_auth() {
+        let ctx = context(10);
+        let mut authed = Session::default();
+
+        let denied = call(
+            &ctx,
+            &mut authed,
+            r#"{"jsonrpc":"2.0","id":1,"method":"shims.list"}"#,
+        )
+        .await;
+        assert_eq!(denied["error"]["code"], UNAUTHORIZED);
+
+        let wrong = call(
+            &ctx,
+            &mut authed,
+            r#"{"jsonrpc":"2.0","id":2,"method":"auth","params":{"token":"guess"}}"#,
+        )
+        .await;
+        assert_eq!(wrong["error"]["code"], UNAUTHORIZED);
+        assert!(!authed.authenticated);
+
+        let ok = call(
+            &ctx,
+            &mut authed,
+            r#"{"jsonrpc":"2.0","id":3,"method":"auth","params":{"token":"secret"}}"#,
+        )
+        .await;
+        assert_eq!(ok["result"]["authenticated"], true);
+
+        let listed = call(
+            &ctx,
+            &mut authed,
+            r#"{"jsonrpc":"2.0","id":4,"method":"shims.list"}"#,
+        )
+        .await;
+        assert_eq!(listed["id"], 4);
+        assert_eq!(listed["result"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_protocol_errors() {
+        let ctx = context(10);
+        let mut authed = Session {
+            authenticated: true,
+            ..Default::default()
+        };
+
+        let parse = call(&ctx, &mut authed, "{not json").await;
+        assert_eq!(parse["error"]["code"], PARSE_ERROR);
+
+        let batch = call(&ctx, &mut authed, "[]").await;
+        assert_eq!(batch["error"]["code"], INVALID_REQUEST);
+
+        let unknown = call(
+            &ctx,
+            &mut authed,
+            r#"{"jsonrpc":"2.0","id":1,"method":"env.install"}"#,
+        )
+        .await;
+        assert_eq!(unknown["error"]["code"], METHOD_NOT_FOUND);
+
+        // Notifications get no reply
+        assert!(handle_message(
+            r#"{"jsonrpc":"2.0","method":"env.list"}"#,
+            &mut authed,
+            &ctx
+        )
+        .await
+        .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_is_per_method() {
+        let ctx = context(2);
+        let mut authed = Session {
+            authenticated: true,
+            ..Default::default()
+        };
+        let list = r#"{"jsonrpc":"2.0","id":1,"method":"env.list"}"#;
+
+        assert!(call(&ctx, &mut authed, list).await.get("result").is_some());
+        assert!(call(&ctx, &mut authed, list).await.get("result").is_some());
+        let limited = call(&ctx, &mut authed, list).await;
+        assert_eq!(limited["error"]["code"], RATE_LIMITED);
+        assert!(limited["error"]["data"]["retryAfterSecs"].as_u64().unwrap() >= 1);
+
+        let other = call(
+            &ctx,
+            &mut authed,
+            r#"{"jsonrpc":"2.0","id":2,"method":"shims.list"}"#,
+        )
+        .await;
+        assert!(other.get("result").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_auth_failures_are_counted_per_connection() {
+        let ctx = context(10);
+        let guess = r#"{"jsonrpc":"2.0","id":1,"method":"auth","params":{"token":"guess"}}"#;
+        let auth = r#"{"jsonrpc":"2.0","id":2,"method":"auth","params":{"token":"secret"}}"#;
+
+        let mut attacker = Session::default();
+        for _ in 0..MAX_AUTH_FAILURES {
+            call(&ctx, &mut attacker, guess).await;
+        }
+        assert!(attacker.locked_out());
+        let refused = call(&ctx, &mut attacker, auth).await;
+        assert_eq!(refused["error"]["code"], UNAUTHORIZED);
+        assert!(!attacker.authenticated);
+
+        let mut client = Session::default();
+        let ok = call(&ctx, &mut client, auth).await;
+        assert_eq!(ok["result"]["authenticated"], true);
+    }
+
+    #[test]
+    fn test_rate_limit_window_resets() {
+        let limiter = RateLimiter::new(1);
+        let start = Instant::now();
+        assert!(limiter.check("env.list", start).is_ok());
+        assert!(limiter.check("env.list", start).is_err());
+        assert!(limiter.check("env.list", start + RATE_LIMIT_WINDOW).is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_token_file_is_user_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = token_path(dir.path());
+        let first = write_token(&path).unwrap();
+        let second = write_token(&path).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), second);
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_socket_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let endpoint = dir.path().join("api.sock").display().to_string();
+        let accept_loop = serve(endpoint.clone(), Arc::new(context(10)))
+            .await
+            .unwrap();
+        let server = tokio::spawn(accept_loop);
+
+        let stream = tokio::net::UnixStream::connect(&endpoint).await.unwrap();
+        let (read_half, mut write_half) = tokio::io::split(stream);
+        let mut lines = BufReader::new(read_half).lines();
+        write_half
+            .write_all(
+                b"{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"auth\",\"params\":{\"token\":\"secret\"}}\n\
+                  {\"jsonrpc\":\"2.0\",\"id\":2,\"method\":\"env.detect\",\"params\":{\"env_type\":\"node\"}}\n",
+            )
+            .await
+            .unwrap();
+
+        let auth: Value = serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+        assert_eq!(auth["id"], 1);
+        let detect: Value =
+            serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+        assert_eq!(detect["result"]["method"], "env.detect");
+        assert_eq!(detect["result"]["params"]["env_type"], "node");
+
+        server.abort();
+    }
+}
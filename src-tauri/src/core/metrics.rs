@@ -0,0 +1,528 @@
+//! Prometheus text-format export of cache, download and install counters.
+//!
+//! Counters are re-derived on every export from the data the app already
+//! persists (the sqlite cache access tables, the download usage ledger and the
+//! install history), so they survive restarts without a separate store. The
+//! optional `/metrics` listener only ever binds to the loopback interface and
+//! only answers requests addressed to a loopback host name.
+
+use crate::cache::download_history::UsagePeriod;
+use crate::cache::{DownloadCache, DownloadHistory, SourceAccessStats};
+use crate::core::history::{
+    HistoryAction, HistoryManager, InstallHistoryEntry, UPDATE_IN_PROGRESS,
+};
+use crate::error::{CogniaError, CogniaResult};
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::future::Future;
+use std::path::Path;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Port used when the listener is enabled without choosing one
+pub const DEFAULT_METRICS_PORT: u16 = 9464;
+
+/// Content type of the Prometheus text exposition format
+pub const METRICS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+const MAX_REQUEST_HEAD_BYTES: usize = 8 * 1024;
+const REQUEST_READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+static APP_STARTED: Lazy<Instant> = Lazy::new(Instant::now);
+static LISTENER: Lazy<StdMutex<Option<RunningListener>>> = Lazy::new(|| StdMutex::new(None));
+
+struct RunningListener {
+    port: u16,
+    handle: tauri::async_runtime::JoinHandle<()>,
+}
+
+/// Pin the uptime reference point; call once during app start-up
+pub fn mark_app_started() {
+    Lazy::force(&APP_STARTED);
+}
+
+/// Seconds since `mark_app_started` (or the first metrics read)
+pub fn uptime_seconds() -> f64 {
+    APP_STARTED.elapsed().as_secs_f64()
+}
+
+/// Completed install history entries for one provider/action/outcome
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstallCounter {
+    pub provider: String,
+    pub action: HistoryAction,
+    pub success: bool,
+    pub count: u64,
+}
+
+/// Everything rendered by `render_prometheus`
+#[derive(Debug, Clone, Default)]
+pub struct MetricsSnapshot {
+    pub cache_access: Vec<SourceAccessStats>,
+    /// Network bytes downloaded per provider
+    pub download_bytes: BTreeMap<String, u64>,
+    pub active_downloads: usize,
+    pub installs: Vec<InstallCounter>,
+    pub uptime_seconds: f64,
+}
+
+/// Group finished install history entries by provider, action and outcome.
+/// Updates that are still running are not counted yet.
+pub fn count_install_outcomes(entries: &[InstallHistoryEntry]) -> Vec<InstallCounter> {
+    let mut counts: BTreeMap<(String, String, bool), (HistoryAction, u64)> = BTreeMap::new();
+    for entry in entries {
+        if !entry.success && entry.error_message.as_deref() == Some(UPDATE_IN_PROGRESS) {
+            continue;
+        }
+        counts
+            .entry((
+                entry.provider.clone(),
+                entry.action.to_string(),
+                entry.success,
+            ))
+            .or_insert((entry.action, 0))
+            .1 += 1;
+    }
+
+    counts
+        .into_iter()
+        .map(|((provider, _, success), (action, count))| InstallCounter {
+            provider,
+            action,
+            success,
+            count,
+        })
+        .collect()
+}
+
+/// Gather a snapshot from the persisted stores under `cache_dir`.
+/// A store that cannot be read contributes no samples instead of failing
+/// the whole export.
+pub async fn collect_snapshot(cache_dir: &Path, active_downloads: usize) -> MetricsSnapshot {
+    let cache_access = match DownloadCache::open(cache_dir).await {
+        Ok(cache) => cache.access_stats_by_source().await.unwrap_or_else(|e| {
+            log::debug!("Metrics: failed to read cache access stats: {}", e);
+            Vec::new()
+        }),
+        Err(e) => {
+            log::debug!("Metrics: failed to open download cache: {}", e);
+            Vec::new()
+        }
+    };
+
+    let download_bytes = match DownloadHistory::open(cache_dir).await {
+        Ok(history) => history
+            .usage_stats(UsagePeriod::All, Utc::now())
+            .by_provider
+            .into_iter()
+            .map(|total| (total.key, total.network_bytes))
+            .collect(),
+        Err(e) => {
+            log::debug!("Metrics: failed to open download history: {}", e);
+            BTreeMap::new()
+        }
+    };
+
+    let installs = match HistoryManager::get_history(None).await {
+        Ok(entries) => count_install_outcomes(&entries),
+        Err(e) => {
+            log::debug!("Metrics: failed to read install history: {}", e);
+            Vec::new()
+        }
+    };
+
+    MetricsSnapshot {
+        cache_access,
+        download_bytes,
+        active_downloads,
+        installs,
+        uptime_seconds: uptime_seconds(),
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn write_header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+/// Render a snapshot as a Prometheus text exposition document
+pub fn render_prometheus(snapshot: &MetricsSnapshot) -> String {
+    let mut out = String::new();
+
+    write_header(
+        &mut out,
+        "cognia_cache_hits_total",
+        "counter",
+        "Cache lookups served from the local cache, by provider.",
+    );
+    for stats in &snapshot.cache_access {
+        let _ = writeln!(
+            out,
+            "cognia_cache_hits_total{{provider=\"{}\"}} {}",
+            escape_label(&stats.source),
+            stats.hits
+        );
+    }
+
+    write_header(
+        &mut out,
+        "cognia_cache_misses_total",
+        "counter",
+        "Cache lookups that found no entry, by provider.",
+    );
+    for stats in &snapshot.cache_access {
+        let _ = writeln!(
+            out,
+            "cognia_cache_misses_total{{provider=\"{}\"}} {}",
+            escape_label(&stats.source),
+            stats.misses
+        );
+    }
+
+    write_header(
+        &mut out,
+        "cognia_download_bytes_total",
+        "counter",
+        "Bytes downloaded over the network, by provider.",
+    );
+    for (provider, bytes) in &snapshot.download_bytes {
+        let _ = writeln!(
+            out,
+            "cognia_download_bytes_total{{provider=\"{}\"}} {}",
+            escape_label(provider),
+            bytes
+        );
+    }
+
+    write_header(
+        &mut out,
+        "cognia_active_downloads",
+        "gauge",
+        "Downloads currently in progress.",
+    );
+    let _ = writeln!(out, "cognia_active_downloads {}", snapshot.active_downloads);
+
+    write_header(
+        &mut out,
+        "cognia_install_operations_total",
+        "counter",
+        "Finished package operations, by provider, action and result.",
+    );
+    for counter in &snapshot.installs {
+        let _ = writeln!(
+            out,
+            "cognia_install_operations_total{{provider=\"{}\",action=\"{}\",result=\"{}\"}} {}",
+            escape_label(&counter.provider),
+            counter.action,
+            if counter.success {
+                "success"
+            } else {
+                "failure"
+            },
+            counter.count
+        );
+    }
+
+    write_header(
+        &mut out,
+        "cognia_uptime_seconds",
+        "gauge",
+        "Seconds since the app started.",
+    );
+    let _ = writeln!(out, "cognia_uptime_seconds {:.3}", snapshot.uptime_seconds);
+
+    out
+}
+
+fn http_response(status: &str, content_type: &str, body: &str, include_body: bool) -> Vec<u8> {
+    let mut response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    )
+    .into_bytes();
+    if include_body {
+        response.extend_from_slice(body.as_bytes());
+    }
+    response
+}
+
+async fn read_request_head(stream: &mut TcpStream) -> Option<String> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+    loop {
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..read]);
+        if buf.windows(4).any(|w| w == b"\r\n\r\n") || buf.len() >= MAX_REQUEST_HEAD_BYTES {
+            break;
+        }
+    }
+    Some(String::from_utf8_lossy(&buf).into_owned())
+}
+
+/// Whether the request's `Host` header names the loopback interface. A web
+/// page can point its own domain at 127.0.0.1 (DNS rebinding), but the
+/// browser still sends that domain as the host.
+fn has_loopback_host(head: &str) -> bool {
+    let Some(host) = head.lines().skip(1).find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim()
+            .eq_ignore_ascii_case("host")
+            .then(|| value.trim())
+    }) else {
+        return false;
+    };
+    let name = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or_default(),
+        None => host.split(':').next().unwrap_or_default(),
+    };
+    name.eq_ignore_ascii_case("localhost") || name == "127.0.0.1" || name == "::1"
+}
+
+async fn handle_connection<F, Fut>(mut stream: TcpStream, render: Arc<F>)
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = String>,
+{
+    let head =
+        match tokio::time::timeout(REQUEST_READ_TIMEOUT, read_request_head(&mut stream)).await {
+            Ok(Some(head)) => head,
+            _ => return,
+        };
+
+    let mut parts = head.lines().next().unwrap_or_default().split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts
+        .next()
+        .unwrap_or_default()
+        .split('?')
+        .next()
+        .unwrap_or_default();
+
+    let response = match (method, path) {
+        _ if !has_loopback_host(&head) => http_response(
+            "403 Forbidden",
+            "text/plain; charset=utf-8",
+            "forbidden\n",
+            true,
+        ),
+        ("GET" | "HEAD", "/metrics") => {
+            let body = render().await;
+            http_response("200 OK", METRICS_CONTENT_TYPE, &body, method == "GET")
+        }
+        (_, "/metrics") => http_response(
+            "405 Method Not Allowed",
+            "text/plain; charset=utf-8",
+            "method not allowed\n",
+            true,
+        ),
+        _ => http_response(
+            "404 Not Found",
+            "text/plain; charset=utf-8",
+            "not found\n",
+            true,
+        ),
+    };
+
+    let _ = stream.write_all(&response).await;
+    let _ = stream.shutdown().await;
+}
+
+/// Accept loop serving `/metrics` on an already bound listener
+pub async fn serve<F, Fut>(listener: TcpListener, render: F)
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = String> + Send + 'static,
+{
+    let render = Arc::new(render);
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(handle_connection(stream, render.clone()));
+            }
+            Err(e) => {
+                log::debug!("Metrics listener accept failed: {}", e);
+            }
+        }
+    }
+}
+
+/// Start, restart or stop the loopback `/metrics` listener so it matches
+/// the given settings. Re-applying the current state is a no-op.
+pub async fn apply_listener<F, Fut>(enabled: bool, port: u16, render: F) -> CogniaResult<()>
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = String> + Send + 'static,
+{
+    {
+        let mut running = LISTENER.lock().unwrap_or_else(|e| e.into_inner());
+        if enabled && running.as_ref().is_some_and(|l| l.port == port) {
+            return Ok(());
+        }
+        if let Some(previous) = running.take() {
+            previous.handle.abort();
+            log::info!("Metrics listener on port {} stopped", previous.port);
+        }
+    }
+
+    if !enabled {
+        return Ok(());
+    }
+
+    let listener = TcpListener::bind(("127.0.0.1", port)).await.map_err(|e| {
+        CogniaError::Internal(format!(
+            "Failed to bind metrics listener on 127.0.0.1:{}: {}",
+            port, e
+        ))
+    })?;
+    let handle = tauri::async_runtime::spawn(serve(listener, render));
+    log::info!("Metrics listener serving http://127.0.0.1:{}/metrics", port);
+
+    let mut running = LISTENER.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(previous) = running.replace(RunningListener { port, handle }) {
+        previous.handle.abort();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history_entry(
+        provider: &str,
+        action: HistoryAction,
+        success: bool,
+        error: Option<&str>,
+    ) -> InstallHistoryEntry {
+        InstallHistoryEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: "pkg".into(),
+            version: "1.0.0".into(),
+            action,
+            timestamp: Utc::now().to_rfc3339(),
+            provider: provider.into(),
+            success,
+            error_message: error.map(str::to_string),
+            actor_plugin_id: None,
+        }
+    }
+
+    #[test]
+    fn test_count_install_outcomes_skips_running_updates() {
+        let entries = vec![
+            history_entry("npm", HistoryAction::Install, true, None),
+            history_entry("npm", HistoryAction::Install, true, None),
+            history_entry("npm", HistoryAction::Install, false, Some("boom")),
+            history_entry(
+                "pip",
+                HistoryAction::Update,
+                false,
+                Some(UPDATE_IN_PROGRESS),
+            ),
+        ];
+
+        let counters = count_install_outcomes(&entries);
+        assert_eq!(
+            counters,
+            vec![
+                InstallCounter {
+                    provider: "npm".into(),
+                    action: HistoryAction::Install,
+                    success: false,
+                    count: 1,
+                },
+                InstallCounter {
+                    provider: "npm".into(),
+                    action: HistoryAction::Install,
+                    success: true,
+                    count: 2,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_prometheus_document() {
+        let snapshot = MetricsSnapshot {
+            cache_access: vec![SourceAccessStats {
+                source: "npm".into(),
+                hits: 3,
+                misses: 1,
+                hit_rate: 0.75,
+            }],
+            download_bytes: BTreeMap::from([("git\"hub".to_string(), 2048)]),
+            active_downloads: 2,
+            installs: vec![InstallCounter {
+                provider: "pip".into(),
+                action: HistoryAction::Install,
+                success: false,
+                count: 4,
+            }],
+            uptime_seconds: 12.5,
+        };
+
+        let text = render_prometheus(&snapshot);
+        assert!(text.contains("# TYPE cognia_cache_hits_total counter\n"));
+        assert!(text.contains("cognia_cache_hits_total{provider=\"npm\"} 3\n"));
+        assert!(text.contains("cognia_cache_misses_total{provider=\"npm\"} 1\n"));
+        assert!(text.contains("cognia_download_bytes_total{provider=\"git\\\"hub\"} 2048\n"));
+        assert!(text.contains("# TYPE cognia_active_downloads gauge\ncognia_active_downloads 2\n"));
+        assert!(text.contains(
+            "cognia_install_operations_total{provider=\"pip\",action=\"install\",result=\"failure\"} 4\n"
+        ));
+        assert!(text.ends_with("cognia_uptime_seconds 12.500\n"));
+    }
+
+    #[tokio::test]
+    async fn test_listener_serves_metrics_only() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve(listener, || async {
+            "cognia_active_downloads 0\n".to_string()
+        }));
+
+        let request = |raw: &'static str| async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream.write_all(raw.as_bytes()).await.unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response
+        };
+
+        let ok = request("GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").await;
+        assert!(ok.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(ok.contains(METRICS_CONTENT_TYPE));
+        assert!(ok.ends_with("\r\n\r\ncognia_active_downloads 0\n"));
+
+        let missing = request("GET /other HTTP/1.1\r\nHost: 127.0.0.1:9464\r\n\r\n").await;
+        assert!(missing.starts_with("HTTP/1.1 404 Not Found\r\n"));
+
+        let post = request("POST /metrics HTTP/1.1\r\nHost: [::1]:9464\r\n\r\n").await;
+        assert!(post.starts_with("HTTP/1.1 405 Method Not Allowed\r\n"));
+
+        let rebound = request("GET /metrics HTTP/1.1\r\nHost: evil.example:9464\r\n\r\n").await;
+        assert!(rebound.starts_with("HTTP/1.1 403 Forbidden\r\n"));
+        assert!(!rebound.contains("cognia_active_downloads"));
+
+        let no_host = request("GET /metrics HTTP/1.0\r\n\r\n").await;
+        assert!(no_host.starts_with("HTTP/1.1 403 Forbidden\r\n"));
+
+        server.abort();
+    }
+}
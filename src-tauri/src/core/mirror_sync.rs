@@ -0,0 +1,1182 @@
+//! Sync configured mirrors and proxies into the package tools' own config
+//! files, so `npm`, `pip`, `cargo`, `gem` and `composer` run outside Cognia
+//! use them too.
+//!
+//! Every write is backed up first and recorded in `<root>/mirror-sync.json`.
+//! Unsync only reverts values that still hold what Cognia wrote, restoring
+//! whatever the user had before.
+
+use crate::config::Settings;
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::{env::dirs_home, fs, proxy};
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::sync::Mutex;
+
+static MIRROR_SYNC_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// Name of the cargo source Cognia points crates.io at
+const CARGO_SOURCE_NAME: &str = "cognia-mirror";
+
+/// Registries the tools use when nothing is configured; replacing one of
+/// these is not treated as overriding a user's mirror
+const UPSTREAM_REGISTRIES: &[&str] = &[
+    "registry.npmjs.org",
+    "pypi.org/simple",
+    "index.crates.io",
+    "crates-io",
+    "rubygems.org",
+    "repo.packagist.org",
+];
+
+/// Lines of unchanged context around each diff hunk
+const DIFF_CONTEXT: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MirrorTool {
+    Npm,
+    Pip,
+    Cargo,
+    Gem,
+    Composer,
+}
+
+impl MirrorTool {
+    pub const ALL: [MirrorTool; 5] = [
+        MirrorTool::Npm,
+        MirrorTool::Pip,
+        MirrorTool::Cargo,
+        MirrorTool::Gem,
+        MirrorTool::Composer,
+    ];
+
+    /// `mirrors.<key>` settings the tool's registry comes from, first match wins
+    fn mirror_keys(self) -> &'static [&'static str] {
+        match self {
+            Self::Npm => &["npm"],
+            Self::Pip => &["pypi", "pip"],
+            Self::Cargo => &["crates", "cargo"],
+            Self::Gem => &["rubygems", "gem"],
+            Self::Composer => &["composer", "packagist"],
+        }
+    }
+
+    fn proxy_provider(self) -> &'static str {
+        match self {
+            Self::Npm => "npm",
+            Self::Pip => "pip",
+            Self::Cargo => "cargo",
+            Self::Gem => "gem",
+            Self::Composer => "composer",
+        }
+    }
+
+    fn format(self) -> ConfigFormat {
+        match self {
+            Self::Npm => ConfigFormat::Npmrc,
+            Self::Pip | Self::Cargo => ConfigFormat::Ini,
+            Self::Gem => ConfigFormat::Gemrc,
+            Self::Composer => ConfigFormat::Json,
+        }
+    }
+
+    /// User-level config file the tool reads
+    pub fn config_path(self) -> Option<PathBuf> {
+        let env_path = |name: &str| {
+            std::env::var_os(name)
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from)
+        };
+        let home = dirs_home();
+        match self {
+            Self::Npm => env_path("NPM_CONFIG_USERCONFIG").or_else(|| Some(home?.join(".npmrc"))),
+            Self::Pip => env_path("PIP_CONFIG_FILE").or_else(|| {
+                if cfg!(windows) {
+                    return Some(env_path("APPDATA")?.join("pip").join("pip.ini"));
+                }
+                let home = home?;
+                let legacy = home.join(".pip").join("pip.conf");
+                if legacy.exists() {
+                    return Some(legacy);
+                }
+                let config = env_path("XDG_CONFIG_HOME").unwrap_or_else(|| home.join(".config"));
+                Some(config.join("pip").join("pip.conf"))
+            }),
+            Self::Cargo => {
+                let cargo_home = env_path("CARGO_HOME").or_else(|| Some(home?.join(".cargo")))?;
+                let legacy = cargo_home.join("config");
+                let path = cargo_home.join("config.toml");
+                Some(if legacy.is_file() && !path.exists() {
+                    legacy
+                } else {
+                    path
+                })
+            }
+            Self::Gem => env_path("GEMRC").or_else(|| Some(home?.join(".gemrc"))),
+            Self::Composer => {
+                let composer_home = env_path("COMPOSER_HOME").or_else(|| {
+                    if cfg!(windows) {
+                        return Some(env_path("APPDATA")?.join("Composer"));
+                    }
+                    let home = home?;
+                    let config =
+                        env_path("XDG_CONFIG_HOME").unwrap_or_else(|| home.join(".config"));
+                    let xdg = config.join("composer");
+                    Some(if xdg.exists() {
+                        xdg
+                    } else {
+                        home.join(".composer")
+                    })
+                })?;
+                Some(composer_home.join("config.json"))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConfigFormat {
+    /// `key=value` lines with no sections
+    Npmrc,
+    /// `[section]` headers and `key = value` lines (pip.conf, cargo TOML)
+    Ini,
+    /// `.gemrc` YAML; only the `:sources:` list is touched
+    Gemrc,
+    /// composer `config.json`; only `repositories.packagist` is touched
+    Json,
+}
+
+/// One value Cognia wants in a tool config
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigEntry {
+    pub section: Option<String>,
+    pub key: String,
+    /// Value as written to the file, e.g. quoted for TOML
+    pub value: String,
+}
+
+impl ConfigEntry {
+    fn new(section: Option<&str>, key: &str, value: impl Into<String>) -> Self {
+        Self {
+            section: section.map(str::to_string),
+            key: key.to_string(),
+            value: value.into(),
+        }
+    }
+
+    fn label(&self) -> String {
+        match &self.section {
+            Some(section) => format!("[{}] {}", section, self.key),
+            None => self.key.clone(),
+        }
+    }
+}
+
+/// An existing value that differs from what sync would write
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MirrorSyncConflict {
+    pub key: String,
+    pub current: String,
+    pub desired: String,
+}
+
+/// What sync or unsync did (or would do) to one tool's config
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MirrorSyncResult {
+    pub tool: MirrorTool,
+    pub path: Option<String>,
+    /// Unified diff of the file, empty when nothing changes
+    pub diff: String,
+    /// Values set by the user to something else; sync skips the tool
+    /// unless forced
+    pub conflicts: Vec<MirrorSyncConflict>,
+    pub applied: bool,
+    pub backup_path: Option<String>,
+    pub message: Option<String>,
+}
+
+impl MirrorSyncResult {
+    fn skipped(tool: MirrorTool, path: Option<&Path>, message: impl Into<String>) -> Self {
+        Self {
+            tool,
+            path: path.map(|p| p.display().to_string()),
+            diff: String::new(),
+            conflicts: Vec::new(),
+            applied: false,
+            backup_path: None,
+            message: Some(message.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RecordedEntry {
+    entry: ConfigEntry,
+    /// Value the file held before Cognia's first write
+    previous: Option<String>,
+}
+
+/// What Cognia wrote to one tool config, so unsync can revert just that
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MirrorSyncRecord {
+    tool: MirrorTool,
+    path: PathBuf,
+    /// The file did not exist before the first sync
+    created_file: bool,
+    entries: Vec<RecordedEntry>,
+    synced_at: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct MirrorSyncState {
+    records: Vec<MirrorSyncRecord>,
+}
+
+fn state_path(root_dir: &Path) -> PathBuf {
+    root_dir.join("mirror-sync.json")
+}
+
+async fn load_state(root_dir: &Path) -> CogniaResult<MirrorSyncState> {
+    Ok(fs::read_json_with_backup(state_path(root_dir))
+        .await?
+        .into_value("mirror sync state")
+        .unwrap_or_default())
+}
+
+async fn save_state(root_dir: &Path, state: &MirrorSyncState) -> CogniaResult<()> {
+    fs::atomic_write_json(state_path(root_dir), state, true).await?;
+    Ok(())
+}
+
+fn mirror_url(settings: &Settings, tool: MirrorTool) -> Option<String> {
+    tool.mirror_keys()
+        .iter()
+        .find_map(|key| settings.get_mirror_url(key))
+}
+
+/// HTTP(S) proxy for the tool; npm, pip and cargo cannot use SOCKS here
+fn http_proxy(settings: &Settings, tool: MirrorTool) -> Option<String> {
+    proxy::effective_proxy(settings, Some(tool.proxy_provider()))
+        .proxy_url
+        .filter(|url| url.starts_with("http://") || url.starts_with("https://"))
+}
+
+fn toml_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Cargo takes a sparse index as `sparse+<url>`; git indexes stay as given
+fn cargo_registry_url(url: &str) -> String {
+    if url.starts_with("sparse+") || url.starts_with("git") || url.ends_with(".git") {
+        url.to_string()
+    } else {
+        format!("sparse+{}", url)
+    }
+}
+
+/// Values `settings` asks for in `tool`'s config
+pub fn desired_entries(settings: &Settings, tool: MirrorTool) -> Vec<ConfigEntry> {
+    let mirror = mirror_url(settings, tool);
+    let proxy = http_proxy(settings, tool);
+    let mut entries = Vec::new();
+    match tool {
+        MirrorTool::Npm => {
+            if let Some(url) = mirror {
+                entries.push(ConfigEntry::new(None, "registry", url));
+            }
+            if let Some(proxy) = proxy {
+                entries.push(ConfigEntry::new(None, "proxy", proxy.clone()));
+                entries.push(ConfigEntry::new(None, "https-proxy", proxy));
+            }
+        }
+        MirrorTool::Pip => {
+            if let Some(url) = mirror {
+                let insecure = tool
+                    .mirror_keys()
+                    .iter()
+                    .find(|key| settings.get_mirror_url(key).is_some())
+                    .is_some_and(|key| !settings.should_verify_ssl(key));
+                let host = reqwest::Url::parse(&url)
+                    .ok()
+                    .and_then(|u| u.host_str().map(str::to_string));
+                entries.push(ConfigEntry::new(Some("global"), "index-url", url));
+                if let Some(host) = host.filter(|_| insecure) {
+                    entries.push(ConfigEntry::new(Some("global"), "trusted-host", host));
+                }
+            }
+            if let Some(proxy) = proxy {
+                entries.push(ConfigEntry::new(Some("global"), "proxy", proxy));
+            }
+        }
+        MirrorTool::Cargo => {
+            if let Some(url) = mirror {
+                let source = format!("source.{}", CARGO_SOURCE_NAME);
+                entries.push(ConfigEntry::new(
+                    Some("source.crates-io"),
+                    "replace-with",
+                    toml_string(CARGO_SOURCE_NAME),
+                ));
+                entries.push(ConfigEntry::new(
+                    Some(&source),
+                    "registry",
+                    toml_string(&cargo_registry_url(&url)),
+                ));
+            }
+            if let Some(proxy) = proxy {
+                entries.push(ConfigEntry::new(Some("http"), "proxy", toml_string(&proxy)));
+            }
+        }
+        MirrorTool::Gem => {
+            if let Some(url) = mirror {
+                entries.push(ConfigEntry::new(None, ":sources", url));
+            }
+        }
+        MirrorTool::Composer => {
+            if let Some(url) = mirror {
+                entries.push(ConfigEntry::new(None, "repositories.packagist", url));
+            }
+        }
+    }
+    entries
+}
+
+// ── Format editing ──────────────────────────────────
+
+fn is_section_header(line: &str) -> Option<&str> {
+    let line = line.trim();
+    let inner = line.strip_prefix('[')?.strip_suffix(']')?;
+    // `[[array]]` tables are headers too, but never the ones we edit
+    Some(inner.trim())
+}
+
+fn split_key_value(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim();
+    if trimmed.starts_with('#') || trimmed.starts_with(';') {
+        return None;
+    }
+    let (key, value) = trimmed.split_once('=')?;
+    Some((key.trim(), value.trim()))
+}
+
+/// Line range `[start, end)` holding `section`'s body; `None` means the lines
+/// before the first header
+fn section_range(lines: &[&str], section: Option<&str>) -> Option<(usize, usize)> {
+    let mut start = match section {
+        None => Some(0),
+        Some(_) => None,
+    };
+    for (i, line) in lines.iter().enumerate() {
+        if let Some(name) = is_section_header(line) {
+            if start.is_some() {
+                return start.map(|s| (s, i));
+            }
+            if Some(name) == section {
+                start = Some(i + 1);
+            }
+        }
+    }
+    start.map(|s| (s, lines.len()))
+}
+
+fn ini_find(lines: &[&str], section: Option<&str>, key: &str) -> Option<usize> {
+    let (start, end) = section_range(lines, section)?;
+    (start..end).find(|&i| split_key_value(lines[i]).is_some_and(|(k, _)| k == key))
+}
+
+fn join_lines(lines: Vec<String>) -> String {
+    let mut out = lines.join("\n");
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out
+}
+
+fn ini_get(content: &str, section: Option<&str>, key: &str) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let i = ini_find(&lines, section, key)?;
+    split_key_value(lines[i]).map(|(_, v)| v.to_string())
+}
+
+fn ini_set(content: &str, section: Option<&str>, key: &str, value: &str, sep: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let line = format!("{}{}{}", key, sep, value);
+    let mut out: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+
+    if let Some(i) = ini_find(&lines, section, key) {
+        out[i] = line;
+    } else if let Some((start, end)) = section_range(&lines, section) {
+        // After the section's last non-blank line
+        let at = (start..end)
+            .rev()
+            .find(|&i| !lines[i].trim().is_empty())
+            .map_or(start, |i| i + 1);
+        out.insert(at, line);
+    } else {
+        // Only a named section can be missing; the top-level range always exists
+        if out.last().is_some_and(|l| !l.trim().is_empty()) {
+            out.push(String::new());
+        }
+        out.push(format!("[{}]", section.unwrap_or_default()));
+        out.push(line);
+    }
+    join_lines(out)
+}
+
+fn ini_remove(content: &str, section: Option<&str>, key: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let Some(i) = ini_find(&lines, section, key) else {
+        return content.to_string();
+    };
+    let mut out: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+    out.remove(i);
+
+    // Drop a section header left with nothing under it
+    if section.is_some() {
+        let remaining: Vec<&str> = out.iter().map(String::as_str).collect();
+        if let Some((start, end)) = section_range(&remaining, section) {
+            if remaining[start..end].iter().all(|l| l.trim().is_empty()) {
+                out.drain(start - 1..end);
+                while out.last().is_some_and(|l| l.trim().is_empty()) {
+                    out.pop();
+                }
+            }
+        }
+    }
+    join_lines(out)
+}
+
+/// Range of the `:sources:` block, header included
+fn gemrc_sources_range(lines: &[&str]) -> Option<(usize, usize)> {
+    let start = lines.iter().position(|l| l.trim_end() == ":sources:")?;
+    let end = (start + 1..lines.len())
+        .find(|&i| !lines[i].trim_start().starts_with("- "))
+        .unwrap_or(lines.len());
+    Some((start, end))
+}
+
+fn gemrc_get(content: &str) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let (start, end) = gemrc_sources_range(&lines)?;
+    let sources: Vec<&str> = lines[start + 1..end]
+        .iter()
+        .map(|l| l.trim_start().trim_start_matches("- ").trim())
+        .collect();
+    Some(sources.join("\n"))
+}
+
+fn gemrc_set(content: &str, sources: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let block: Vec<String> = std::iter::once(":sources:".to_string())
+        .chain(sources.lines().map(|s| format!("- {}", s)))
+        .collect();
+    let mut out: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+    match gemrc_sources_range(&lines) {
+        Some((start, end)) => {
+            out.splice(start..end, block);
+        }
+        None => {
+            if out.is_empty() {
+                out.push("---".to_string());
+            }
+            out.extend(block);
+        }
+    }
+    join_lines(out)
+}
+
+fn gemrc_remove(content: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let Some((start, end)) = gemrc_sources_range(&lines) else {
+        return content.to_string();
+    };
+    let mut out: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+    out.drain(start..end);
+    if out.iter().all(|l| l.trim().is_empty() || l.trim() == "---") {
+        out.clear();
+    }
+    join_lines(out)
+}
+
+fn parse_json_object(content: &str) -> CogniaResult<serde_json::Map<String, serde_json::Value>> {
+    if content.trim().is_empty() {
+        return Ok(serde_json::Map::new());
+    }
+    match serde_json::from_str(content) {
+        Ok(serde_json::Value::Object(map)) => Ok(map),
+        Ok(_) => Err(CogniaError::Parse("Config is not a JSON object".into())),
+        Err(e) => Err(CogniaError::Parse(format!("Invalid JSON config: {}", e))),
+    }
+}
+
+fn to_pretty_json(map: serde_json::Map<String, serde_json::Value>) -> CogniaResult<String> {
+    // Composer writes its config with four-space indentation
+    let mut buf = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
+    let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+    serde::Serialize::serialize(&serde_json::Value::Object(map), &mut ser)
+        .map_err(|e| CogniaError::Parse(e.to_string()))?;
+    let mut out = String::from_utf8(buf).map_err(|e| CogniaError::Parse(e.to_string()))?;
+    out.push('\n');
+    Ok(out)
+}
+
+fn composer_get(content: &str) -> Option<String> {
+    let map = parse_json_object(content).ok()?;
+    let url = map.get("repositories")?.get("packagist")?.get("url")?;
+    url.as_str().map(str::to_string)
+}
+
+fn composer_set(content: &str, url: &str) -> CogniaResult<String> {
+    let mut map = parse_json_object(content)?;
+    let repositories = map
+        .entry("repositories")
+        .or_insert_with(|| serde_json::json!({}));
+    let Some(repositories) = repositories.as_object_mut() else {
+        return Err(CogniaError::Parse(
+            "composer `repositories` is a list; set the packagist mirror by hand".into(),
+        ));
+    };
+    repositories.insert(
+        "packagist".into(),
+        serde_json::json!({ "type": "composer", "url": url }),
+    );
+    to_pretty_json(map)
+}
+
+fn composer_remove(content: &str) -> CogniaResult<String> {
+    let mut map = parse_json_object(content)?;
+    if let Some(repositories) = map.get_mut("repositories").and_then(|r| r.as_object_mut()) {
+        repositories.remove("packagist");
+        if repositories.is_empty() {
+            map.remove("repositories");
+        }
+    }
+    if map.is_empty() {
+        return Ok(String::new());
+    }
+    to_pretty_json(map)
+}
+
+fn get_value(format: ConfigFormat, content: &str, entry: &ConfigEntry) -> Option<String> {
+    let section = entry.section.as_deref();
+    match format {
+        ConfigFormat::Npmrc | ConfigFormat::Ini => ini_get(content, section, &entry.key),
+        ConfigFormat::Gemrc => gemrc_get(content),
+        ConfigFormat::Json => composer_get(content),
+    }
+}
+
+fn set_value(
+    format: ConfigFormat,
+    content: &str,
+    entry: &ConfigEntry,
+    value: &str,
+) -> CogniaResult<String> {
+    let section = entry.section.as_deref();
+    Ok(match format {
+        ConfigFormat::Npmrc => ini_set(content, section, &entry.key, value, "="),
+        ConfigFormat::Ini => ini_set(content, section, &entry.key, value, " = "),
+        ConfigFormat::Gemrc => gemrc_set(content, value),
+        ConfigFormat::Json => composer_set(content, value)?,
+    })
+}
+
+fn remove_value(format: ConfigFormat, content: &str, entry: &ConfigEntry) -> CogniaResult<String> {
+    let section = entry.section.as_deref();
+    Ok(match format {
+        ConfigFormat::Npmrc | ConfigFormat::Ini => ini_remove(content, section, &entry.key),
+        ConfigFormat::Gemrc => gemrc_remove(content),
+        ConfigFormat::Json => composer_remove(content)?,
+    })
+}
+
+fn normalize_value(value: &str) -> String {
+    value
+        .trim()
+        .trim_matches('"')
+        .trim_matches('\'')
+        .trim_end_matches('/')
+        .to_string()
+}
+
+fn same_value(a: &str, b: &str) -> bool {
+    normalize_value(a) == normalize_value(b)
+}
+
+fn is_upstream(value: &str) -> bool {
+    let value = normalize_value(value);
+    value.lines().all(|line| {
+        UPSTREAM_REGISTRIES
+            .iter()
+            .any(|upstream| line.ends_with(upstream))
+    })
+}
+
+// ── Diff ────────────────────────────────────────────
+
+/// Unified diff of `old` and `new` with a few lines of context
+pub fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    // Longest common subsequence table, built from the end
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    // (tag, old line no, new line no, text)
+    let mut ops: Vec<(char, usize, usize, &str)> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            ops.push((' ', i, j, a[i]));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(('-', i, j, a[i]));
+            i += 1;
+        } else {
+            ops.push(('+', i, j, b[j]));
+            j += 1;
+        }
+    }
+
+    let mut out = format!("--- {}\n+++ {}\n", path, path);
+    let changed: Vec<usize> = (0..ops.len()).filter(|&k| ops[k].0 != ' ').collect();
+    let mut k = 0;
+    while k < changed.len() {
+        let start = changed[k].saturating_sub(DIFF_CONTEXT);
+        let mut end = changed[k];
+        while k + 1 < changed.len() && changed[k + 1] <= end + 2 * DIFF_CONTEXT + 1 {
+            k += 1;
+            end = changed[k];
+        }
+        let end = (end + DIFF_CONTEXT + 1).min(ops.len());
+        let hunk = &ops[start..end];
+        let old_len = hunk.iter().filter(|op| op.0 != '+').count();
+        let new_len = hunk.iter().filter(|op| op.0 != '-').count();
+        let old_start = if old_len == 0 {
+            hunk[0].1
+        } else {
+            hunk[0].1 + 1
+        };
+        let new_start = if new_len == 0 {
+            hunk[0].2
+        } else {
+            hunk[0].2 + 1
+        };
+        out.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_start, old_len, new_start, new_len
+        ));
+        for (tag, _, _, text) in hunk {
+            out.push(*tag);
+            out.push_str(text);
+            out.push('\n');
+        }
+        k += 1;
+    }
+    out
+}
+
+// ── Sync / unsync ───────────────────────────────────
+
+/// New content for a sync and the user's values it would override
+fn plan_sync(
+    format: ConfigFormat,
+    content: &str,
+    entries: &[ConfigEntry],
+    record: Option<&MirrorSyncRecord>,
+) -> CogniaResult<(String, Vec<MirrorSyncConflict>)> {
+    let mut next = content.to_string();
+    let mut conflicts = Vec::new();
+    for entry in entries {
+        let current = get_value(format, content, entry);
+        let written_before = record
+            .and_then(|r| r.entries.iter().find(|e| e.entry.key == entry.key))
+            .map(|e| e.entry.value.as_str());
+        match current {
+            Some(current) if same_value(&current, &entry.value) => continue,
+            Some(current)
+                if written_before.map_or(true, |w| !same_value(w, &current))
+                    && !is_upstream(&current) =>
+            {
+                conflicts.push(MirrorSyncConflict {
+                    key: entry.label(),
+                    current,
+                    desired: entry.value.clone(),
+                });
+            }
+            _ => {}
+        }
+        next = set_value(format, &next, entry, &entry.value)?;
+    }
+    Ok((next, conflicts))
+}
+
+async fn read_config(path: &Path) -> CogniaResult<Option<String>> {
+    if !fs::exists(path).await {
+        return Ok(None);
+    }
+    Ok(Some(fs::read_file_string(path).await?))
+}
+
+/// Back up `path` and replace it atomically with `content`; `remove`
+/// deletes the file instead. A symlinked config, as dotfile managers
+/// leave, is written through to its target.
+async fn write_config(path: &Path, content: &str, remove: bool) -> CogniaResult<Option<PathBuf>> {
+    let backup = if fs::exists(path).await {
+        Some(crate::core::terminal::backup_shell_config(path).await?)
+    } else {
+        None
+    };
+    if remove {
+        fs::remove_file(path).await?;
+    } else {
+        let target = match tokio::fs::canonicalize(path).await {
+            Ok(target) => target,
+            Err(_) => path.to_path_buf(),
+        };
+        fs::write_file_atomic(&target, content.as_bytes()).await?;
+    }
+    Ok(backup)
+}
+
+/// Write `settings`' mirrors and proxies into each tool's config. With
+/// `preview` nothing is written. A tool whose config already points at a
+/// different mirror is skipped unless `force` is set.
+pub async fn sync_tools(
+    root_dir: &Path,
+    settings: &Settings,
+    tools: &[MirrorTool],
+    preview: bool,
+    force: bool,
+) -> CogniaResult<Vec<MirrorSyncResult>> {
+    sync_tools_at(
+        root_dir,
+        settings,
+        tools,
+        preview,
+        force,
+        MirrorTool::config_path,
+    )
+    .await
+}
+
+/// [`sync_tools`] with each tool's config looked up through `config_path`
+async fn sync_tools_at(
+    root_dir: &Path,
+    settings: &Settings,
+    tools: &[MirrorTool],
+    preview: bool,
+    force: bool,
+    config_path: impl Fn(MirrorTool) -> Option<PathBuf>,
+) -> CogniaResult<Vec<MirrorSyncResult>> {
+    let _guard = MIRROR_SYNC_LOCK.lock().await;
+    let mut state = load_state(root_dir).await?;
+    let mut results = Vec::new();
+
+    for &tool in tools {
+        let path = config_path(tool);
+        let entries = desired_entries(settings, tool);
+        if entries.is_empty() {
+            results.push(MirrorSyncResult::skipped(
+                tool,
+                path.as_deref(),
+                "No mirror or proxy configured",
+            ));
+            continue;
+        }
+        let Some(path) = path else {
+            results.push(MirrorSyncResult::skipped(
+                tool,
+                None,
+                "Config location not found",
+            ));
+            continue;
+        };
+
+        let existing = read_config(&path).await?;
+        let content = existing.clone().unwrap_or_default();
+        let record_index = state
+            .records
+            .iter()
+            .position(|r| r.tool == tool && r.path == path);
+        let record = record_index.map(|i| &state.records[i]);
+        let (next, conflicts) = match plan_sync(tool.format(), &content, &entries, record) {
+            Ok(plan) => plan,
+            Err(e) => {
+                results.push(MirrorSyncResult::skipped(tool, Some(&path), e.to_string()));
+                continue;
+            }
+        };
+        let path_display = path.display().to_string();
+        let mut result = MirrorSyncResult {
+            tool,
+            path: Some(path_display.clone()),
+            diff: unified_diff(&path_display, &content, &next),
+            conflicts,
+            applied: false,
+            backup_path: None,
+            message: None,
+        };
+
+        if result.diff.is_empty() {
+            result.message = Some("Already in sync".into());
+        } else if !result.conflicts.is_empty() && !force {
+            result.message = Some("Config sets a different mirror; use force to overwrite".into());
+        } else if !preview {
+            let backup = write_config(&path, &next, false).await?;
+            result.backup_path = backup.map(|p| p.display().to_string());
+            result.applied = true;
+
+            let recorded: Vec<RecordedEntry> = entries
+                .iter()
+                .map(|entry| {
+                    // Keep the value from before Cognia's first write
+                    let previous = record
+                        .and_then(|r| r.entries.iter().find(|e| e.entry.key == entry.key))
+                        .map(|e| e.previous.clone())
+                        .unwrap_or_else(|| get_value(tool.format(), &content, entry));
+                    RecordedEntry {
+                        entry: entry.clone(),
+                        previous,
+                    }
+                })
+                .collect();
+            let created_file = record.map_or(existing.is_none(), |r| r.created_file);
+            let new_record = MirrorSyncRecord {
+                tool,
+                path: path.clone(),
+                created_file,
+                entries: recorded,
+                synced_at: Utc::now().to_rfc3339(),
+            };
+            match record_index {
+                Some(i) => state.records[i] = new_record,
+                None => state.records.push(new_record),
+            }
+        }
+        results.push(result);
+    }
+
+    if !preview {
+        save_state(root_dir, &state).await?;
+    }
+    Ok(results)
+}
+
+/// Revert what [`sync_tools`] wrote to each tool's config. Values the user
+/// changed since are left alone.
+pub async fn unsync_tools(
+    root_dir: &Path,
+    tools: &[MirrorTool],
+) -> CogniaResult<Vec<MirrorSyncResult>> {
+    unsync_tools_at(root_dir, tools, MirrorTool::config_path).await
+}
+
+/// [`unsync_tools`] with each tool's config looked up through `config_path`
+async fn unsync_tools_at(
+    root_dir: &Path,
+    tools: &[MirrorTool],
+    config_path: impl Fn(MirrorTool) -> Option<PathBuf>,
+) -> CogniaResult<Vec<MirrorSyncResult>> {
+    let _guard = MIRROR_SYNC_LOCK.lock().await;
+    let mut state = load_state(root_dir).await?;
+    let mut results = Vec::new();
+    let mut kept = Vec::new();
+
+    for record in std::mem::take(&mut state.records) {
+        if !tools.contains(&record.tool) {
+            kept.push(record);
+            continue;
+        }
+        let format = record.tool.format();
+        let path_display = record.path.display().to_string();
+        let content = read_config(&record.path).await?.unwrap_or_default();
+
+        let mut next = content.clone();
+        let mut untouched = Vec::new();
+        for recorded in record.entries.iter().rev() {
+            let entry = &recorded.entry;
+            match get_value(format, &next, entry) {
+                Some(current) if same_value(&current, &entry.value) => {
+                    next = match &recorded.previous {
+                        Some(previous) => set_value(format, &next, entry, previous)?,
+                        None => remove_value(format, &next, entry)?,
+                    };
+                }
+                Some(_) => untouched.push(entry.label()),
+                None => {}
+            }
+        }
+        // A file Cognia created and nothing else uses goes away again
+        let remove = record.created_file && next.trim().is_empty();
+        if remove {
+            next.clear();
+        }
+
+        let diff = unified_diff(&path_display, &content, &next);
+        let backup_path = if diff.is_empty() {
+            None
+        } else {
+            write_config(&record.path, &next, remove).await?
+        };
+        results.push(MirrorSyncResult {
+            tool: record.tool,
+            path: Some(path_display),
+            applied: !diff.is_empty(),
+            diff,
+            conflicts: Vec::new(),
+            backup_path: backup_path.map(|p| p.display().to_string()),
+            message: (!untouched.is_empty())
+                .then(|| format!("Changed since sync, left as is: {}", untouched.join(", "))),
+        });
+    }
+
+    for &tool in tools {
+        if !results.iter().any(|r| r.tool == tool) {
+            results.push(MirrorSyncResult::skipped(
+                tool,
+                config_path(tool).as_deref(),
+                "Nothing synced by Cognia",
+            ));
+        }
+    }
+
+    state.records = kept;
+    save_state(root_dir, &state).await?;
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MirrorConfig;
+
+    fn settings_with(mirrors: &[(&str, &str)]) -> Settings {
+        let mut settings = Settings::default();
+        for (key, url) in mirrors {
+            settings
+                .mirrors
+                .insert(key.to_string(), MirrorConfig::new(*url));
+        }
+        settings
+    }
+
+    #[test]
+    fn test_ini_set_replaces_inserts_and_appends() {
+        let pip = "[global]\ntimeout = 60\n\n[install]\nuser = true\n";
+        let set = ini_set(
+            pip,
+            Some("global"),
+            "index-url",
+            "https://mirror/simple",
+            " = ",
+        );
+        assert_eq!(
+            set,
+            "[global]\ntimeout = 60\nindex-url = https://mirror/simple\n\n[install]\nuser = true\n"
+        );
+        let replaced = ini_set(&set, Some("global"), "index-url", "https://other", " = ");
+        assert!(replaced.contains("index-url = https://other\n"));
+        assert!(!replaced.contains("mirror/simple"));
+
+        let appended = ini_set("", Some("http"), "proxy", "\"http://p:8080\"", " = ");
+        assert_eq!(appended, "[http]\nproxy = \"http://p:8080\"\n");
+
+        let npmrc = ini_set("save-exact=true\n", None, "registry", "https://r/", "=");
+        assert_eq!(npmrc, "save-exact=true\nregistry=https://r/\n");
+    }
+
+    #[test]
+    fn test_ini_remove_drops_empty_section() {
+        let cargo =
+            "[net]\nretry = 3\n\n[source.cognia-mirror]\nregistry = \"sparse+https://m/\"\n";
+        let removed = ini_remove(cargo, Some("source.cognia-mirror"), "registry");
+        assert_eq!(removed, "[net]\nretry = 3\n");
+        assert_eq!(ini_remove("a=1\n", None, "b"), "a=1\n");
+    }
+
+    #[test]
+    fn test_ini_get_ignores_other_sections_and_comments() {
+        let content = "# registry=https://commented\n[other]\nregistry = x\n";
+        assert_eq!(ini_get(content, None, "registry"), None);
+        assert_eq!(
+            ini_get(content, Some("other"), "registry"),
+            Some("x".into())
+        );
+    }
+
+    #[test]
+    fn test_gemrc_sources_roundtrip() {
+        let gemrc = "---\n:sources:\n- https://rubygems.org/\n:verbose: true\n";
+        assert_eq!(gemrc_get(gemrc).as_deref(), Some("https://rubygems.org/"));
+        let set = gemrc_set(gemrc, "https://gems.ruby-china.com/");
+        assert_eq!(
+            set,
+            "---\n:sources:\n- https://gems.ruby-china.com/\n:verbose: true\n"
+        );
+        assert_eq!(gemrc_remove(&set), "---\n:verbose: true\n");
+        assert_eq!(gemrc_remove(":sources:\n- https://m/\n"), "");
+    }
+
+    #[test]
+    fn test_composer_packagist_roundtrip() {
+        let set = composer_set("{\"config\": {\"process-timeout\": 600}}", "https://m/").unwrap();
+        assert_eq!(composer_get(&set).as_deref(), Some("https://m/"));
+        assert!(set.contains("    \"repositories\""));
+        let removed = composer_remove(&set).unwrap();
+        assert_eq!(composer_get(&removed), None);
+        assert!(removed.contains("process-timeout"));
+        assert_eq!(
+            composer_remove(&composer_set("", "https://m/").unwrap()).unwrap(),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_desired_entries() {
+        let mut settings = settings_with(&[
+            ("npm", "https://registry.npmmirror.com/"),
+            ("pypi", "https://pypi.tuna.tsinghua.edu.cn/simple"),
+            ("crates", "https://rsproxy.cn/index/"),
+        ]);
+        settings.network.proxy = Some("http://127.0.0.1:7890".into());
+
+        let npm = desired_entries(&settings, MirrorTool::Npm);
+        let keys: Vec<&str> = npm.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, vec!["registry", "proxy", "https-proxy"]);
+
+        let cargo = desired_entries(&settings, MirrorTool::Cargo);
+        assert_eq!(cargo[0].value, "\"cognia-mirror\"");
+        assert_eq!(cargo[1].section.as_deref(), Some("source.cognia-mirror"));
+        assert_eq!(cargo[1].value, "\"sparse+https://rsproxy.cn/index/\"");
+        assert_eq!(cargo[2].section.as_deref(), Some("http"));
+
+        assert!(desired_entries(&settings, MirrorTool::Gem).is_empty());
+
+        settings.network.proxy = Some("socks5://127.0.0.1:1080".into());
+        assert_eq!(desired_entries(&settings, MirrorTool::Pip).len(), 1);
+    }
+
+    #[test]
+    fn test_plan_sync_flags_user_mirror_but_not_upstream() {
+        let entries = vec![ConfigEntry::new(None, "registry", "https://mirror/")];
+        let (next, conflicts) = plan_sync(
+            ConfigFormat::Npmrc,
+            "registry=https://registry.npmjs.org/\n",
+            &entries,
+            None,
+        )
+        .unwrap();
+        assert!(conflicts.is_empty());
+        assert_eq!(next, "registry=https://mirror/\n");
+
+        let (_, conflicts) = plan_sync(
+            ConfigFormat::Npmrc,
+            "registry=https://corp/\n",
+            &entries,
+            None,
+        )
+        .unwrap();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].current, "https://corp/");
+
+        // A value Cognia wrote earlier is not the user's
+        let record = MirrorSyncRecord {
+            tool: MirrorTool::Npm,
+            path: PathBuf::from(".npmrc"),
+            created_file: false,
+            entries: vec![RecordedEntry {
+                entry: ConfigEntry::new(None, "registry", "https://old-mirror/"),
+                previous: None,
+            }],
+            synced_at: String::new(),
+        };
+        let (_, conflicts) = plan_sync(
+            ConfigFormat::Npmrc,
+            "registry=https://old-mirror/\n",
+            &entries,
+            Some(&record),
+        )
+        .unwrap();
+        assert!(conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_unified_diff() {
+        assert_eq!(unified_diff("f", "a\n", "a\n"), "");
+        let diff = unified_diff("f", "a\nb\nc\n", "a\nB\nc\nd\n");
+        assert_eq!(diff, "--- f\n+++ f\n@@ -1,3 +1,4 @@\n a\n-b\n+B\n c\n+d\n");
+        let created = unified_diff("f", "", "x=1\n");
+        assert_eq!(created, "--- f\n+++ f\n@@ -0,0 +1,1 @@\n+x=1\n");
+    }
+
+    #[tokio::test]
+    async fn test_sync_and_unsync_restore_user_lines() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join("root");
+        let npmrc = tmp.path().join(".npmrc");
+        std::fs::write(&npmrc, "save-exact=true\nregistry=https://corp/\n").unwrap();
+        let config_path = |_: MirrorTool| Some(npmrc.clone());
+
+        let settings = settings_with(&[("npm", "https://registry.npmmirror.com/")]);
+        let tools = [MirrorTool::Npm];
+
+        let blocked = sync_tools_at(&root, &settings, &tools, false, false, config_path)
+            .await
+            .unwrap();
+        assert!(!blocked[0].applied);
+        assert_eq!(blocked[0].conflicts.len(), 1);
+
+        let preview = sync_tools_at(&root, &settings, &tools, true, true, config_path)
+            .await
+            .unwrap();
+        assert!(!preview[0].applied);
+        assert!(preview[0]
+            .diff
+            .contains("+registry=https://registry.npmmirror.com/"));
+        assert!(std::fs::read_to_string(&npmrc)
+            .unwrap()
+            .contains("https://corp/"));
+
+        let synced = sync_tools_at(&root, &settings, &tools, false, true, config_path)
+            .await
+            .unwrap();
+        assert!(synced[0].applied);
+        assert!(synced[0].backup_path.is_some());
+
+        // Lines the user adds afterwards survive unsync
+        let mut content = std::fs::read_to_string(&npmrc).unwrap();
+        content.push_str("fund=false\n");
+        std::fs::write(&npmrc, content).unwrap();
+
+        let unsynced = unsync_tools_at(&root, &tools, config_path).await.unwrap();
+        assert!(unsynced[0].applied);
+        assert_eq!(
+            std::fs::read_to_string(&npmrc).unwrap(),
+            "save-exact=true\nregistry=https://corp/\nfund=false\n"
+        );
+        let again = unsync_tools_at(&root, &tools, config_path).await.unwrap();
+        assert!(!again[0].applied);
+    }
+}
@@ -1,18 +1,50 @@
+pub mod archive;
 pub mod backup;
 pub mod batch;
+pub mod conda_env_spec;
 pub mod custom_detection;
+pub mod custom_env_type;
+pub mod deep_link;
+pub mod env_disk_usage;
+pub mod env_resolution;
 pub mod environment;
 pub mod eol;
+pub mod eol_advice;
+pub mod full_backup;
 pub mod health_check;
 pub mod history;
+pub mod install_validation;
 pub mod installer;
+pub mod launch_monitor;
+pub mod local_api;
+pub mod metrics;
+pub mod mirror_sync;
+pub mod node_globals;
+pub mod notifications;
+pub mod onboarding;
+pub mod operation_queue;
 pub mod orchestrator;
+pub mod package_try;
+pub mod path_shadow;
+pub mod pin_file;
 pub mod profiles;
 pub mod project_env_detect;
+pub mod project_tasks;
+pub mod python_tools;
+pub mod release_notes;
 pub mod shim;
+pub mod smoke_test;
+pub mod stacks;
 pub mod system_info;
+pub mod team_config;
 pub mod terminal;
+pub mod terminal_history;
+pub mod terminal_session;
+pub mod trash;
 pub mod validation;
+pub mod version_import;
+pub mod version_sync;
+pub mod workspace;
 
 pub use batch::*;
 pub use custom_detection::*;
@@ -0,0 +1,386 @@
+//! Global packages across the Node package managers.
+//!
+//! npm, pnpm, Yarn and Bun each keep their own global root. Listing asks
+//! every manager found on the Node version's PATH and records which one owns
+//! each package, so a migration to another Node version reinstalls it with
+//! the same manager. Yarn Berry (v2+) has no global installs at all; only
+//! Yarn Classic globals are handled.
+
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::process::{self, ProcessOptions};
+use crate::provider::node_base::{
+    parse_installed_packages_from_json_output, parse_node_list_entry, parse_node_list_json_output,
+};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const QUERY_TIMEOUT: Duration = Duration::from_secs(30);
+const INSTALL_TIMEOUT: Duration = Duration::from_secs(300);
+/// Bundled with Node itself rather than installed by the user
+const BUNDLED_PACKAGES: [&str; 2] = ["npm", "corepack"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NodePackageManager {
+    Npm,
+    Pnpm,
+    Yarn,
+    Bun,
+}
+
+impl NodePackageManager {
+    pub const ALL: [NodePackageManager; 4] = [Self::Npm, Self::Pnpm, Self::Yarn, Self::Bun];
+
+    pub fn binary(self) -> &'static str {
+        match self {
+            Self::Npm => "npm",
+            Self::Pnpm => "pnpm",
+            Self::Yarn => "yarn",
+            Self::Bun => "bun",
+        }
+    }
+
+    /// Parse a manager name, also accepting a `packageManager` spec such as
+    /// `pnpm@9.1.0+sha512...`
+    pub fn parse(input: &str) -> Option<Self> {
+        let name = input.trim().split('@').next().unwrap_or("");
+        Self::ALL
+            .into_iter()
+            .find(|manager| manager.binary().eq_ignore_ascii_case(name))
+    }
+}
+
+/// A global package and the manager that installed it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeGlobalPackage {
+    pub name: String,
+    pub version: String,
+    /// Executables from the package's `bin` field
+    pub bins: Vec<String>,
+    pub manager: NodePackageManager,
+}
+
+fn query_opts(base: &ProcessOptions) -> ProcessOptions {
+    base.clone().with_timeout(QUERY_TIMEOUT)
+}
+
+async fn run(
+    manager: NodePackageManager,
+    args: &[&str],
+    opts: ProcessOptions,
+) -> CogniaResult<String> {
+    let output = process::execute(manager.binary(), args, Some(opts)).await?;
+    if output.success {
+        Ok(output.stdout)
+    } else {
+        Err(CogniaError::Provider(format!(
+            "{} {} failed: {}",
+            manager.binary(),
+            args.join(" "),
+            output.stderr.trim()
+        )))
+    }
+}
+
+async fn yarn_is_berry(base: &ProcessOptions) -> bool {
+    run(NodePackageManager::Yarn, &["--version"], query_opts(base))
+        .await
+        .ok()
+        .and_then(|version| version.trim().split('.').next()?.parse::<u32>().ok())
+        .is_some_and(|major| major >= 2)
+}
+
+/// Managers that answer `--version` with the given environment
+pub async fn available_managers(base: &ProcessOptions) -> Vec<NodePackageManager> {
+    let mut available = Vec::new();
+    for manager in NodePackageManager::ALL {
+        if run(manager, &["--version"], query_opts(base)).await.is_ok() {
+            available.push(manager);
+        }
+    }
+    available
+}
+
+/// The `packageManager` field of a package.json, if it names a known manager
+pub fn manager_from_package_json(content: &str) -> Option<NodePackageManager> {
+    let json: serde_json::Value = serde_json::from_str(content).ok()?;
+    NodePackageManager::parse(json.get("packageManager")?.as_str()?)
+}
+
+/// The manager to use when none is given: the nearest `packageManager`
+/// field above `project_dir` (corepack provides that manager even when it
+/// is not installed), then npm, then any other installed manager.
+pub async fn detect_preferred_manager(
+    project_dir: Option<&Path>,
+    available: &[NodePackageManager],
+) -> Option<NodePackageManager> {
+    if let Some(dir) = project_dir {
+        for ancestor in dir.ancestors() {
+            let Ok(content) = tokio::fs::read_to_string(ancestor.join("package.json")).await else {
+                continue;
+            };
+            if let Some(manager) = manager_from_package_json(&content) {
+                return Some(manager);
+            }
+        }
+    }
+    if available.contains(&NodePackageManager::Npm) {
+        return Some(NodePackageManager::Npm);
+    }
+    available.first().copied()
+}
+
+/// The directory holding the manager's global `node_modules` entries
+async fn global_root(manager: NodePackageManager, base: &ProcessOptions) -> Option<PathBuf> {
+    let root = match manager {
+        NodePackageManager::Npm | NodePackageManager::Pnpm => {
+            run(manager, &["root", "-g"], query_opts(base)).await.ok()?
+        }
+        NodePackageManager::Yarn => {
+            let dir = run(manager, &["global", "dir"], query_opts(base))
+                .await
+                .ok()?;
+            return Some(PathBuf::from(dir.trim()).join("node_modules"));
+        }
+        NodePackageManager::Bun => {
+            let install = base
+                .env
+                .get("BUN_INSTALL")
+                .cloned()
+                .or_else(|| std::env::var("BUN_INSTALL").ok())
+                .map(PathBuf::from)
+                .or_else(|| crate::platform::fs::get_home_dir().map(|home| home.join(".bun")))?;
+            return Some(install.join("install").join("global").join("node_modules"));
+        }
+    };
+    let root = root.trim();
+    (!root.is_empty()).then(|| PathBuf::from(root))
+}
+
+/// Executable names declared by a package.json `bin` field
+pub fn bin_names(package_name: &str, package_json: &str) -> Vec<String> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(package_json) else {
+        return vec![];
+    };
+    match json.get("bin") {
+        // A string bin is named after the package, without its scope
+        Some(serde_json::Value::String(_)) => {
+            let name = package_name.rsplit('/').next().unwrap_or(package_name);
+            vec![name.to_string()]
+        }
+        Some(serde_json::Value::Object(bins)) => {
+            let mut names: Vec<String> = bins.keys().cloned().collect();
+            names.sort();
+            names
+        }
+        _ => vec![],
+    }
+}
+
+/// `name@version` pairs from a manager's global listing output
+pub fn parse_global_list(manager: NodePackageManager, output: &str) -> Vec<(String, String)> {
+    let entries = match manager {
+        NodePackageManager::Npm | NodePackageManager::Pnpm => {
+            parse_installed_packages_from_json_output(output).unwrap_or_default()
+        }
+        NodePackageManager::Yarn => parse_node_list_json_output(output),
+        NodePackageManager::Bun => output.lines().filter_map(parse_node_list_entry).collect(),
+    };
+    entries
+        .into_iter()
+        .filter(|(name, _)| !BUNDLED_PACKAGES.contains(&name.as_str()))
+        .collect()
+}
+
+/// Global packages installed by one manager
+pub async fn list_globals(
+    manager: NodePackageManager,
+    base: &ProcessOptions,
+) -> CogniaResult<Vec<NodeGlobalPackage>> {
+    let args: &[&str] = match manager {
+        NodePackageManager::Npm | NodePackageManager::Pnpm => &["ls", "-g", "--json", "--depth=0"],
+        NodePackageManager::Yarn => {
+            if yarn_is_berry(base).await {
+                return Ok(vec![]);
+            }
+            &["global", "list", "--json"]
+        }
+        NodePackageManager::Bun => &["pm", "ls", "-g"],
+    };
+    let output = run(manager, args, query_opts(base)).await?;
+    let root = global_root(manager, base).await;
+
+    let mut packages = Vec::new();
+    for (name, version) in parse_global_list(manager, &output) {
+        let bins = match &root {
+            Some(root) => tokio::fs::read_to_string(root.join(&name).join("package.json"))
+                .await
+                .map(|content| bin_names(&name, &content))
+                .unwrap_or_default(),
+            None => vec![],
+        };
+        packages.push(NodeGlobalPackage {
+            name,
+            version,
+            bins,
+            manager,
+        });
+    }
+    Ok(packages)
+}
+
+/// Global packages from every available manager
+pub async fn list_all_globals(
+    managers: &[NodePackageManager],
+    base: &ProcessOptions,
+) -> Vec<NodeGlobalPackage> {
+    let mut packages = Vec::new();
+    for &manager in managers {
+        match list_globals(manager, base).await {
+            Ok(found) => packages.extend(found),
+            Err(e) => log::debug!("Listing {} globals failed: {}", manager.binary(), e),
+        }
+    }
+    packages
+}
+
+fn berry_unsupported() -> CogniaError {
+    CogniaError::Provider(
+        "Yarn Berry (v2+) does not support global packages. Use npm, pnpm or bun instead.".into(),
+    )
+}
+
+/// Install `spec` (a name, optionally `@version`) globally
+pub async fn install_global(
+    manager: NodePackageManager,
+    spec: &str,
+    base: &ProcessOptions,
+) -> CogniaResult<()> {
+    let args: Vec<&str> = match manager {
+        NodePackageManager::Npm => vec!["install", "-g", spec],
+        NodePackageManager::Pnpm | NodePackageManager::Bun => vec!["add", "-g", spec],
+        NodePackageManager::Yarn => {
+            if yarn_is_berry(base).await {
+                return Err(berry_unsupported());
+            }
+            vec!["global", "add", spec]
+        }
+    };
+    run(manager, &args, base.clone().with_timeout(INSTALL_TIMEOUT)).await?;
+    Ok(())
+}
+
+/// Update a global package to its latest version
+pub async fn update_global(
+    manager: NodePackageManager,
+    name: &str,
+    base: &ProcessOptions,
+) -> CogniaResult<()> {
+    if manager == NodePackageManager::Yarn {
+        if yarn_is_berry(base).await {
+            return Err(berry_unsupported());
+        }
+        let args = ["global", "upgrade", name, "--latest"];
+        run(manager, &args, base.clone().with_timeout(INSTALL_TIMEOUT)).await?;
+        return Ok(());
+    }
+    install_global(manager, &format!("{}@latest", name), base).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manager_names_and_specs() {
+        assert_eq!(
+            NodePackageManager::parse("pnpm@9.1.0+sha512.abc"),
+            Some(NodePackageManager::Pnpm)
+        );
+        assert_eq!(
+            NodePackageManager::parse("Yarn"),
+            Some(NodePackageManager::Yarn)
+        );
+        assert_eq!(NodePackageManager::parse("deno"), None);
+        assert_eq!(
+            manager_from_package_json(r#"{"name":"app","packageManager":"bun@1.1.0"}"#),
+            Some(NodePackageManager::Bun)
+        );
+        assert_eq!(manager_from_package_json(r#"{"name":"app"}"#), None);
+    }
+
+    #[tokio::test]
+    async fn test_detect_preferred_manager() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("packages").join("cli");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{"packageManager":"pnpm@9.0.0"}"#,
+        )
+        .unwrap();
+
+        let npm_only = [NodePackageManager::Npm];
+        assert_eq!(
+            detect_preferred_manager(Some(&nested), &npm_only).await,
+            Some(NodePackageManager::Pnpm)
+        );
+        assert_eq!(
+            detect_preferred_manager(None, &[NodePackageManager::Bun, NodePackageManager::Npm])
+                .await,
+            Some(NodePackageManager::Npm)
+        );
+        assert_eq!(
+            detect_preferred_manager(None, &[NodePackageManager::Bun]).await,
+            Some(NodePackageManager::Bun)
+        );
+        assert_eq!(detect_preferred_manager(None, &[]).await, None);
+    }
+
+    #[test]
+    fn test_parse_global_list_per_manager() {
+        let npm =
+            r#"{"dependencies":{"npm":{"version":"10.2.0"},"typescript":{"version":"5.4.5"}}}"#;
+        assert_eq!(
+            parse_global_list(NodePackageManager::Npm, npm),
+            vec![("typescript".to_string(), "5.4.5".to_string())]
+        );
+
+        let pnpm = r#"[{"path":"/g","dependencies":{"@vue/cli":{"version":"5.0.8"}}}]"#;
+        assert_eq!(
+            parse_global_list(NodePackageManager::Pnpm, pnpm),
+            vec![("@vue/cli".to_string(), "5.0.8".to_string())]
+        );
+
+        let yarn = r#"{"type":"tree","data":{"trees":[{"name":"eslint@8.57.0"}]}}"#;
+        assert_eq!(
+            parse_global_list(NodePackageManager::Yarn, yarn),
+            vec![("eslint".to_string(), "8.57.0".to_string())]
+        );
+
+        let bun = "/home/u/.bun/install/global node_modules (2)\n├── prettier@3.2.5\n└── @biomejs/biome@1.7.0\n";
+        assert_eq!(
+            parse_global_list(NodePackageManager::Bun, bun),
+            vec![
+                ("prettier".to_string(), "3.2.5".to_string()),
+                ("@biomejs/biome".to_string(), "1.7.0".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bin_names() {
+        assert_eq!(
+            bin_names("@scope/tool", r#"{"bin":"./cli.js"}"#),
+            vec!["tool"]
+        );
+        assert_eq!(
+            bin_names("typescript", r#"{"bin":{"tsserver":"a","tsc":"b"}}"#),
+            vec!["tsc", "tsserver"]
+        );
+        assert!(bin_names("lib", r#"{"main":"index.js"}"#).is_empty());
+        assert!(bin_names("broken", "not json").is_empty());
+    }
+}
@@ -0,0 +1,584 @@
+//! Notification history ("notification center").
+//!
+//! Every notification the backend raises — download results, available
+//! updates, EOL warnings, install failures — is recorded here with its
+//! category, severity and payload, whether or not a desktop toast was shown.
+//! Records are persisted to a small SQLite table next to the job history.
+
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::fs;
+use serde::{Deserialize, Serialize};
+use sqlx::{sqlite::SqlitePoolOptions, FromRow, SqlitePool};
+use std::path::{Path, PathBuf};
+use tokio::sync::RwLock;
+
+/// Notifications kept in the database; older rows are pruned on open
+const MAX_NOTIFICATION_HISTORY: i64 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationCategory {
+    Download,
+    Update,
+    Eol,
+    InstallFailure,
+    System,
+}
+
+impl NotificationCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Download => "download",
+            Self::Update => "update",
+            Self::Eol => "eol",
+            Self::InstallFailure => "install_failure",
+            Self::System => "system",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "download" => Some(Self::Download),
+            "update" => Some(Self::Update),
+            "eol" => Some(Self::Eol),
+            "install_failure" => Some(Self::InstallFailure),
+            "system" => Some(Self::System),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationSeverity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl NotificationSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Success => "success",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "info" => Some(Self::Info),
+            "success" => Some(Self::Success),
+            "warning" => Some(Self::Warning),
+            "error" => Some(Self::Error),
+            _ => None,
+        }
+    }
+
+    /// Warnings and errors pass the tray's "important only" level
+    pub fn is_important(&self) -> bool {
+        *self >= Self::Warning
+    }
+}
+
+/// How notifications of one category are delivered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationDelivery {
+    /// Recorded and shown as a native desktop toast
+    #[default]
+    Toast,
+    /// Recorded and listed in the app, no desktop toast
+    InApp,
+    /// Recorded as already read, no toast
+    Muted,
+}
+
+impl NotificationDelivery {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Toast => "toast",
+            Self::InApp => "in_app",
+            Self::Muted => "muted",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "toast" => Some(Self::Toast),
+            "in_app" => Some(Self::InApp),
+            "muted" => Some(Self::Muted),
+            _ => None,
+        }
+    }
+}
+
+/// A notification raised by backend code, before it is recorded
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub category: NotificationCategory,
+    pub severity: NotificationSeverity,
+    pub title: String,
+    pub body: String,
+    pub payload: serde_json::Value,
+}
+
+impl Notification {
+    pub fn new(
+        category: NotificationCategory,
+        severity: NotificationSeverity,
+        title: impl Into<String>,
+        body: impl Into<String>,
+    ) -> Self {
+        Self {
+            category,
+            severity,
+            title: title.into(),
+            body: body.into(),
+            payload: serde_json::Value::Null,
+        }
+    }
+
+    pub fn with_payload(mut self, payload: serde_json::Value) -> Self {
+        self.payload = payload;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationRecord {
+    pub id: String,
+    pub category: NotificationCategory,
+    pub severity: NotificationSeverity,
+    pub title: String,
+    pub body: String,
+    pub payload: serde_json::Value,
+    pub created_at: String,
+    pub read: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationFilter {
+    pub category: Option<NotificationCategory>,
+    pub severity: Option<NotificationSeverity>,
+    #[serde(default)]
+    pub unread_only: bool,
+    pub limit: Option<usize>,
+}
+
+impl NotificationFilter {
+    fn matches(&self, record: &NotificationRecord) -> bool {
+        self.category.map_or(true, |c| record.category == c)
+            && self.severity.map_or(true, |s| record.severity == s)
+            && (!self.unread_only || !record.read)
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct NotificationRow {
+    id: String,
+    category: String,
+    severity: String,
+    title: String,
+    body: String,
+    payload: String,
+    created_at: String,
+    read: bool,
+}
+
+impl NotificationRow {
+    fn into_record(self) -> Option<NotificationRecord> {
+        Some(NotificationRecord {
+            category: NotificationCategory::parse(&self.category)?,
+            severity: NotificationSeverity::parse(&self.severity)?,
+            payload: serde_json::from_str(&self.payload).unwrap_or(serde_json::Value::Null),
+            id: self.id,
+            title: self.title,
+            body: self.body,
+            created_at: self.created_at,
+            read: self.read,
+        })
+    }
+}
+
+/// Notification history backed by SQLite.
+///
+/// The store is attached after startup (`attach_store`); notifications
+/// raised before that are held in memory and written when it attaches.
+#[derive(Default)]
+pub struct NotificationCenter {
+    pool: RwLock<Option<SqlitePool>>,
+    pending: RwLock<Vec<NotificationRecord>>,
+}
+
+pub type SharedNotificationCenter = std::sync::Arc<NotificationCenter>;
+
+impl NotificationCenter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Default database location, next to the job history
+    pub fn default_db_path() -> Option<PathBuf> {
+        fs::get_cognia_dir().map(|dir| dir.join("state").join("notifications.db"))
+    }
+
+    pub async fn attach_store(&self, db_path: &Path) -> CogniaResult<()> {
+        if let Some(parent) = db_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let url = format!("sqlite:{}?mode=rwc", db_path.display());
+        let pool = SqlitePoolOptions::new()
+            .max_connections(2)
+            .connect(&url)
+            .await
+            .map_err(|e| {
+                CogniaError::Internal(format!("Failed to open notification database: {}", e))
+            })?;
+
+        sqlx::query("PRAGMA busy_timeout = 5000")
+            .execute(&pool)
+            .await
+            .map_err(|e| CogniaError::Internal(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS notifications (
+                id TEXT PRIMARY KEY,
+                category TEXT NOT NULL,
+                severity TEXT NOT NULL,
+                title TEXT NOT NULL,
+                body TEXT NOT NULL,
+                payload TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                read INTEGER NOT NULL DEFAULT 0
+            );
+            CREATE INDEX IF NOT EXISTS idx_notifications_created ON notifications(created_at);
+            CREATE INDEX IF NOT EXISTS idx_notifications_read ON notifications(read);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| {
+            CogniaError::Internal(format!("Failed to create notifications table: {}", e))
+        })?;
+
+        sqlx::query(
+            "DELETE FROM notifications WHERE id NOT IN \
+             (SELECT id FROM notifications ORDER BY created_at DESC LIMIT ?)",
+        )
+        .bind(MAX_NOTIFICATION_HISTORY)
+        .execute(&pool)
+        .await
+        .map_err(|e| CogniaError::Internal(e.to_string()))?;
+
+        let pending = std::mem::take(&mut *self.pending.write().await);
+        for record in &pending {
+            Self::write_record(&pool, record).await?;
+        }
+
+        *self.pool.write().await = Some(pool);
+        Ok(())
+    }
+
+    async fn write_record(pool: &SqlitePool, record: &NotificationRecord) -> CogniaResult<()> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO notifications
+                (id, category, severity, title, body, payload, created_at, read)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&record.id)
+        .bind(record.category.as_str())
+        .bind(record.severity.as_str())
+        .bind(&record.title)
+        .bind(&record.body)
+        .bind(record.payload.to_string())
+        .bind(&record.created_at)
+        .bind(record.read)
+        .execute(pool)
+        .await
+        .map_err(|e| CogniaError::Internal(format!("Failed to persist notification: {}", e)))?;
+        Ok(())
+    }
+
+    /// Record a notification. Muted notifications are stored as read so
+    /// they stay in the history without raising the unread count.
+    pub async fn record(
+        &self,
+        notification: Notification,
+        delivery: NotificationDelivery,
+    ) -> CogniaResult<NotificationRecord> {
+        let record = NotificationRecord {
+            id: uuid::Uuid::new_v4().to_string(),
+            category: notification.category,
+            severity: notification.severity,
+            title: notification.title,
+            body: notification.body,
+            payload: notification.payload,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            read: delivery == NotificationDelivery::Muted,
+        };
+
+        match self.pool.read().await.as_ref() {
+            Some(pool) => Self::write_record(pool, &record).await?,
+            None => self.pending.write().await.push(record.clone()),
+        }
+        Ok(record)
+    }
+
+    /// Newest first
+    pub async fn list(&self, filter: &NotificationFilter) -> CogniaResult<Vec<NotificationRecord>> {
+        let mut records: Vec<NotificationRecord> = match self.pool.read().await.clone() {
+            Some(pool) => {
+                let rows: Vec<NotificationRow> =
+                    sqlx::query_as("SELECT * FROM notifications ORDER BY created_at DESC")
+                        .fetch_all(&pool)
+                        .await
+                        .map_err(|e| CogniaError::Internal(e.to_string()))?;
+                rows.into_iter()
+                    .filter_map(NotificationRow::into_record)
+                    .collect()
+            }
+            None => {
+                let mut pending = self.pending.read().await.clone();
+                pending.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+                pending
+            }
+        };
+
+        records.retain(|record| filter.matches(record));
+        if let Some(limit) = filter.limit {
+            records.truncate(limit);
+        }
+        Ok(records)
+    }
+
+    /// Mark the given notifications (or all when `ids` is `None`) as read.
+    /// Returns the number of notifications that changed.
+    pub async fn mark_read(&self, ids: Option<&[String]>) -> CogniaResult<usize> {
+        let Some(pool) = self.pool.read().await.clone() else {
+            let mut pending = self.pending.write().await;
+            let mut changed = 0;
+            for record in pending.iter_mut() {
+                if !record.read && ids.map_or(true, |ids| ids.contains(&record.id)) {
+                    record.read = true;
+                    changed += 1;
+                }
+            }
+            return Ok(changed);
+        };
+
+        let affected = match ids {
+            None => sqlx::query("UPDATE notifications SET read = 1 WHERE read = 0")
+                .execute(&pool)
+                .await
+                .map_err(|e| CogniaError::Internal(e.to_string()))?
+                .rows_affected(),
+            Some(ids) => {
+                let mut affected = 0;
+                for id in ids {
+                    affected +=
+                        sqlx::query("UPDATE notifications SET read = 1 WHERE id = ? AND read = 0")
+                            .bind(id)
+                            .execute(&pool)
+                            .await
+                            .map_err(|e| CogniaError::Internal(e.to_string()))?
+                            .rows_affected();
+                }
+                affected
+            }
+        };
+        Ok(affected as usize)
+    }
+
+    /// Delete the given notifications (or all when `ids` is `None`).
+    /// Returns the number of notifications removed.
+    pub async fn clear(&self, ids: Option<&[String]>) -> CogniaResult<usize> {
+        let Some(pool) = self.pool.read().await.clone() else {
+            let mut pending = self.pending.write().await;
+            let before = pending.len();
+            pending.retain(|record| ids.is_some_and(|ids| !ids.contains(&record.id)));
+            return Ok(before - pending.len());
+        };
+
+        let affected = match ids {
+            None => sqlx::query("DELETE FROM notifications")
+                .execute(&pool)
+                .await
+                .map_err(|e| CogniaError::Internal(e.to_string()))?
+                .rows_affected(),
+            Some(ids) => {
+                let mut affected = 0;
+                for id in ids {
+                    affected += sqlx::query("DELETE FROM notifications WHERE id = ?")
+                        .bind(id)
+                        .execute(&pool)
+                        .await
+                        .map_err(|e| CogniaError::Internal(e.to_string()))?
+                        .rows_affected();
+                }
+                affected
+            }
+        };
+        Ok(affected as usize)
+    }
+
+    pub async fn unread_count(&self) -> CogniaResult<usize> {
+        let Some(pool) = self.pool.read().await.clone() else {
+            return Ok(self
+                .pending
+                .read()
+                .await
+                .iter()
+                .filter(|record| !record.read)
+                .count());
+        };
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM notifications WHERE read = 0")
+            .fetch_one(&pool)
+            .await
+            .map_err(|e| CogniaError::Internal(e.to_string()))?;
+        Ok(count as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn download_done(name: &str) -> Notification {
+        Notification::new(
+            NotificationCategory::Download,
+            NotificationSeverity::Success,
+            "Download Complete",
+            format!("{} has finished downloading.", name),
+        )
+        .with_payload(serde_json::json!({ "name": name }))
+    }
+
+    #[tokio::test]
+    async fn test_record_list_and_filter() {
+        let dir = tempdir().unwrap();
+        let center = NotificationCenter::new();
+        center
+            .attach_store(&dir.path().join("notifications.db"))
+            .await
+            .unwrap();
+
+        center
+            .record(download_done("node.zip"), NotificationDelivery::Toast)
+            .await
+            .unwrap();
+        center
+            .record(
+                Notification::new(
+                    NotificationCategory::Eol,
+                    NotificationSeverity::Warning,
+                    "End of life",
+                    "python 3.8 is end-of-life",
+                ),
+                NotificationDelivery::InApp,
+            )
+            .await
+            .unwrap();
+
+        let all = center.list(&NotificationFilter::default()).await.unwrap();
+        assert_eq!(all.len(), 2);
+
+        let downloads = center
+            .list(&NotificationFilter {
+                category: Some(NotificationCategory::Download),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(downloads.len(), 1);
+        assert_eq!(downloads[0].payload["name"], "node.zip");
+        assert!(!downloads[0].read);
+    }
+
+    #[tokio::test]
+    async fn test_muted_notifications_are_recorded_as_read() {
+        let center = NotificationCenter::new();
+        center
+            .record(download_done("a"), NotificationDelivery::Muted)
+            .await
+            .unwrap();
+        center
+            .record(download_done("b"), NotificationDelivery::Toast)
+            .await
+            .unwrap();
+
+        assert_eq!(center.unread_count().await.unwrap(), 1);
+        let unread = center
+            .list(&NotificationFilter {
+                unread_only: true,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(unread.len(), 1);
+        assert_eq!(unread[0].payload["name"], "b");
+    }
+
+    #[tokio::test]
+    async fn test_mark_read_and_clear() {
+        let dir = tempdir().unwrap();
+        let center = NotificationCenter::new();
+        center
+            .attach_store(&dir.path().join("notifications.db"))
+            .await
+            .unwrap();
+
+        let first = center
+            .record(download_done("a"), NotificationDelivery::Toast)
+            .await
+            .unwrap();
+        center
+            .record(download_done("b"), NotificationDelivery::Toast)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            center.mark_read(Some(&[first.id.clone()])).await.unwrap(),
+            1
+        );
+        assert_eq!(center.unread_count().await.unwrap(), 1);
+        assert_eq!(center.mark_read(None).await.unwrap(), 1);
+        assert_eq!(center.unread_count().await.unwrap(), 0);
+
+        assert_eq!(center.clear(Some(&[first.id])).await.unwrap(), 1);
+        assert_eq!(center.clear(None).await.unwrap(), 1);
+        assert!(center
+            .list(&NotificationFilter::default())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pending_notifications_are_persisted_on_attach() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("notifications.db");
+
+        let center = NotificationCenter::new();
+        center
+            .record(download_done("early"), NotificationDelivery::Toast)
+            .await
+            .unwrap();
+        center.attach_store(&db_path).await.unwrap();
+
+        let reopened = NotificationCenter::new();
+        reopened.attach_store(&db_path).await.unwrap();
+        let records = reopened.list(&NotificationFilter::default()).await.unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].title, "Download Complete");
+        assert_eq!(reopened.unread_count().await.unwrap(), 1);
+    }
+}
@@ -0,0 +1,305 @@
+//! First-run onboarding: ordered setup steps whose progress is kept in the
+//! settings file, so an interrupted setup resumes at the first pending step.
+
+use crate::config::OnboardingSettings;
+use crate::error::{CogniaError, CogniaResult};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Setup steps in the order they run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingStep {
+    /// Choose (or keep) the data root directory
+    RootDir,
+    /// Mirror and proxy settings
+    Mirrors,
+    /// Put the shim directory on PATH for the chosen shells
+    PathSetup,
+    /// First scan for system-installed environments
+    EnvDetect,
+    /// Opt in or out of sending feedback and diagnostics
+    Feedback,
+}
+
+impl OnboardingStep {
+    pub const ALL: [Self; 5] = [
+        Self::RootDir,
+        Self::Mirrors,
+        Self::PathSetup,
+        Self::EnvDetect,
+        Self::Feedback,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RootDir => "root_dir",
+            Self::Mirrors => "mirrors",
+            Self::PathSetup => "path_setup",
+            Self::EnvDetect => "env_detect",
+            Self::Feedback => "feedback",
+        }
+    }
+
+    /// The root directory decides where everything else goes, so it has to
+    /// be confirmed; completing it without a payload keeps the default
+    pub fn skippable(&self) -> bool {
+        !matches!(self, Self::RootDir)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingStepStatus {
+    #[default]
+    Pending,
+    Completed,
+    Skipped,
+}
+
+/// Persisted state of one step
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OnboardingStepRecord {
+    pub status: OnboardingStepStatus,
+    /// RFC 3339 time the step was last completed or skipped
+    pub updated_at: Option<String>,
+    /// Short summary of what completing the step did
+    pub detail: Option<String>,
+}
+
+/// Payload of the `root_dir` step
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RootDirPayload {
+    /// Data root; `None` keeps the current one
+    pub root_dir: Option<String>,
+}
+
+/// Payload of the `mirrors` step: config keys to set, limited to
+/// `mirrors.*`, `network.proxy` and `network.no_proxy`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MirrorsPayload {
+    pub values: HashMap<String, String>,
+}
+
+/// Payload of the `path_setup` step
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PathSetupPayload {
+    /// Shell ids (`bash`, `zsh`, `fish`, ...); empty means the login shell
+    pub shells: Vec<String>,
+}
+
+/// Payload of the `feedback` step
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FeedbackPayload {
+    pub opt_in: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnboardingStepState {
+    pub step: OnboardingStep,
+    pub status: OnboardingStepStatus,
+    pub updated_at: Option<String>,
+    pub detail: Option<String>,
+    pub skippable: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnboardingStatus {
+    pub steps: Vec<OnboardingStepState>,
+    /// First pending step, `None` once every step is completed or skipped
+    pub current_step: Option<OnboardingStep>,
+    pub finished: bool,
+    pub configured_shells: Vec<String>,
+    pub feedback_opt_in: bool,
+}
+
+impl OnboardingSettings {
+    pub fn record(&self, step: OnboardingStep) -> &OnboardingStepRecord {
+        match step {
+            OnboardingStep::RootDir => &self.root_dir,
+            OnboardingStep::Mirrors => &self.mirrors,
+            OnboardingStep::PathSetup => &self.path_setup,
+            OnboardingStep::EnvDetect => &self.env_detect,
+            OnboardingStep::Feedback => &self.feedback,
+        }
+    }
+
+    pub fn record_mut(&mut self, step: OnboardingStep) -> &mut OnboardingStepRecord {
+        match step {
+            OnboardingStep::RootDir => &mut self.root_dir,
+            OnboardingStep::Mirrors => &mut self.mirrors,
+            OnboardingStep::PathSetup => &mut self.path_setup,
+            OnboardingStep::EnvDetect => &mut self.env_detect,
+            OnboardingStep::Feedback => &mut self.feedback,
+        }
+    }
+}
+
+/// Deserialize a step payload; a missing payload is the step's default
+pub fn parse_payload<T: DeserializeOwned + Default>(
+    step: OnboardingStep,
+    payload: Option<serde_json::Value>,
+) -> CogniaResult<T> {
+    match payload {
+        None | Some(serde_json::Value::Null) => Ok(T::default()),
+        Some(value) => serde_json::from_value(value).map_err(|e| {
+            CogniaError::Config(format!(
+                "Invalid payload for onboarding step '{}': {}",
+                step.as_str(),
+                e
+            ))
+        }),
+    }
+}
+
+/// Whether the `mirrors` step may set `key`
+pub fn is_mirrors_step_key(key: &str) -> bool {
+    key.starts_with("mirrors.") || key == "network.proxy" || key == "network.no_proxy"
+}
+
+/// Check that `step` may be completed (or skipped) now. Steps run in order;
+/// steps already completed or skipped can be run again.
+pub fn check_transition(
+    onboarding: &OnboardingSettings,
+    step: OnboardingStep,
+    skip: bool,
+) -> CogniaResult<()> {
+    if skip && !step.skippable() {
+        return Err(CogniaError::Config(format!(
+            "Onboarding step '{}' cannot be skipped",
+            step.as_str()
+        )));
+    }
+    let earlier_pending = OnboardingStep::ALL
+        .iter()
+        .take_while(|s| **s != step)
+        .find(|s| onboarding.record(**s).status == OnboardingStepStatus::Pending);
+    match earlier_pending {
+        Some(earlier) => Err(CogniaError::Config(format!(
+            "Complete or skip onboarding step '{}' before '{}'",
+            earlier.as_str(),
+            step.as_str()
+        ))),
+        None => Ok(()),
+    }
+}
+
+pub fn mark(
+    onboarding: &mut OnboardingSettings,
+    step: OnboardingStep,
+    status: OnboardingStepStatus,
+    detail: Option<String>,
+) {
+    *onboarding.record_mut(step) = OnboardingStepRecord {
+        status,
+        updated_at: Some(chrono::Utc::now().to_rfc3339()),
+        detail,
+    };
+}
+
+pub fn status(onboarding: &OnboardingSettings) -> OnboardingStatus {
+    let steps: Vec<OnboardingStepState> = OnboardingStep::ALL
+        .iter()
+        .map(|step| {
+            let record = onboarding.record(*step);
+            OnboardingStepState {
+                step: *step,
+                status: record.status,
+                updated_at: record.updated_at.clone(),
+                detail: record.detail.clone(),
+                skippable: step.skippable(),
+            }
+        })
+        .collect();
+    let current_step = steps
+        .iter()
+        .find(|s| s.status == OnboardingStepStatus::Pending)
+        .map(|s| s.step);
+    OnboardingStatus {
+        finished: current_step.is_none(),
+        current_step,
+        steps,
+        configured_shells: onboarding.configured_shells.clone(),
+        feedback_opt_in: onboarding.feedback_opt_in,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps_run_in_order_and_resume() {
+        let mut onboarding = OnboardingSettings::default();
+        assert_eq!(
+            status(&onboarding).current_step,
+            Some(OnboardingStep::RootDir)
+        );
+        assert!(check_transition(&onboarding, OnboardingStep::Mirrors, false).is_err());
+        assert!(check_transition(&onboarding, OnboardingStep::RootDir, true).is_err());
+
+        mark(
+            &mut onboarding,
+            OnboardingStep::RootDir,
+            OnboardingStepStatus::Completed,
+            None,
+        );
+        mark(
+            &mut onboarding,
+            OnboardingStep::Mirrors,
+            OnboardingStepStatus::Skipped,
+            None,
+        );
+        assert!(check_transition(&onboarding, OnboardingStep::PathSetup, false).is_ok());
+        assert!(check_transition(&onboarding, OnboardingStep::EnvDetect, false).is_err());
+        // Completed and skipped steps can be run again
+        assert!(check_transition(&onboarding, OnboardingStep::RootDir, false).is_ok());
+        assert!(check_transition(&onboarding, OnboardingStep::Mirrors, false).is_ok());
+
+        let current = status(&onboarding);
+        assert_eq!(current.current_step, Some(OnboardingStep::PathSetup));
+        assert!(!current.finished);
+
+        for step in [
+            OnboardingStep::PathSetup,
+            OnboardingStep::EnvDetect,
+            OnboardingStep::Feedback,
+        ] {
+            mark(&mut onboarding, step, OnboardingStepStatus::Completed, None);
+        }
+        let current = status(&onboarding);
+        assert!(current.finished);
+        assert_eq!(current.current_step, None);
+    }
+
+    #[test]
+    fn test_parse_payload() {
+        let payload: PathSetupPayload = parse_payload(
+            OnboardingStep::PathSetup,
+            Some(serde_json::json!({ "shells": ["bash", "fish"] })),
+        )
+        .unwrap();
+        assert_eq!(payload.shells, vec!["bash", "fish"]);
+
+        let payload: FeedbackPayload = parse_payload(OnboardingStep::Feedback, None).unwrap();
+        assert!(!payload.opt_in);
+
+        assert!(parse_payload::<FeedbackPayload>(
+            OnboardingStep::Feedback,
+            Some(serde_json::json!({ "optIn": "yes" })),
+        )
+        .is_err());
+
+        assert!(is_mirrors_step_key("mirrors.npm"));
+        assert!(is_mirrors_step_key("network.proxy"));
+        assert!(!is_mirrors_step_key("paths.root"));
+    }
+}
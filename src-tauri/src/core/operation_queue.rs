@@ -0,0 +1,715 @@
+//! App-wide registry of long-running operations ("jobs").
+//!
+//! Commands such as `env_install`, `batch_install`, `cache_migrate` and
+//! `wsl_export` register a job when they start and finish it when they
+//! return, giving the UI a single activity view. Jobs are persisted to a
+//! SQLite table next to the install history, so a job that was running when
+//! the app exited is reported as `interrupted` on the next start.
+
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::fs;
+use serde::{Deserialize, Serialize};
+use sqlx::{sqlite::SqlitePoolOptions, FromRow, SqlitePool};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Finished jobs kept in the database; older rows are pruned on open
+const MAX_JOB_HISTORY: i64 = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    EnvInstall,
+    BatchInstall,
+    CacheMigrate,
+    CacheVerify,
+    WslExport,
+}
+
+impl JobKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::EnvInstall => "env_install",
+            Self::BatchInstall => "batch_install",
+            Self::CacheMigrate => "cache_migrate",
+            Self::CacheVerify => "cache_verify",
+            Self::WslExport => "wsl_export",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "env_install" => Some(Self::EnvInstall),
+            "batch_install" => Some(Self::BatchInstall),
+            "cache_migrate" => Some(Self::CacheMigrate),
+            "cache_verify" => Some(Self::CacheVerify),
+            "wsl_export" => Some(Self::WslExport),
+            _ => None,
+        }
+    }
+
+    /// Whether re-running an interrupted job with the same params picks up
+    /// where it left off instead of redoing or corrupting work. Cache
+    /// migration resumes from its journal and cache verification skips
+    /// files it already verified; the others start over.
+    pub fn is_resumable(&self) -> bool {
+        matches!(self, Self::CacheMigrate | Self::CacheVerify)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+    /// Was running when the app exited
+    Interrupted,
+}
+
+impl JobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+            Self::Interrupted => "interrupted",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "running" => Some(Self::Running),
+            "completed" => Some(Self::Completed),
+            "failed" => Some(Self::Failed),
+            "cancelled" => Some(Self::Cancelled),
+            "interrupted" => Some(Self::Interrupted),
+            _ => None,
+        }
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        !matches!(self, Self::Running)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobRecord {
+    pub id: String,
+    pub kind: JobKind,
+    /// Human-readable summary, e.g. `Install node 20.11.0`
+    pub title: String,
+    /// Arguments the job was started with; enough to re-run it
+    pub params: serde_json::Value,
+    pub status: JobStatus,
+    /// 0–100 when the operation reports progress
+    pub progress: Option<f64>,
+    pub message: Option<String>,
+    pub error: Option<String>,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    /// Interrupted job that can be safely resumed by re-running it
+    pub resumable: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobLifecycle {
+    Started,
+    Progress,
+    Finished,
+}
+
+/// Payload of the `job-updated` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobEvent {
+    pub lifecycle: JobLifecycle,
+    pub job: JobRecord,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobQuery {
+    pub kind: Option<JobKind>,
+    pub status: Option<JobStatus>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, FromRow)]
+struct JobRow {
+    id: String,
+    kind: String,
+    title: String,
+    params: String,
+    status: String,
+    progress: Option<f64>,
+    message: Option<String>,
+    error: Option<String>,
+    started_at: String,
+    finished_at: Option<String>,
+    resumable: bool,
+}
+
+impl JobRow {
+    fn into_record(self) -> Option<JobRecord> {
+        Some(JobRecord {
+            kind: JobKind::parse(&self.kind)?,
+            status: JobStatus::parse(&self.status)?,
+            params: serde_json::from_str(&self.params).unwrap_or(serde_json::Value::Null),
+            id: self.id,
+            title: self.title,
+            progress: self.progress,
+            message: self.message,
+            error: self.error,
+            started_at: self.started_at,
+            finished_at: self.finished_at,
+            resumable: self.resumable,
+        })
+    }
+}
+
+type JobListener = Arc<dyn Fn(JobEvent) + Send + Sync>;
+
+struct ActiveJob {
+    record: JobRecord,
+    cancel: Arc<AtomicBool>,
+}
+
+/// Tracks running jobs in memory and persists every job to SQLite.
+///
+/// The store is attached after startup (`attach_store`); until then jobs
+/// are only tracked in memory.
+#[derive(Default)]
+pub struct OperationQueue {
+    pool: RwLock<Option<SqlitePool>>,
+    active: std::sync::RwLock<HashMap<String, ActiveJob>>,
+    listener: std::sync::RwLock<Option<JobListener>>,
+}
+
+pub type SharedOperationQueue = Arc<OperationQueue>;
+
+impl OperationQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Default database location, next to the install history
+    pub fn default_db_path() -> Option<PathBuf> {
+        fs::get_cognia_dir().map(|dir| dir.join("state").join("jobs.db"))
+    }
+
+    /// Open the job database, mark jobs left running by a previous session
+    /// as interrupted and prune old history. Returns the interrupted jobs.
+    pub async fn attach_store(&self, db_path: &Path) -> CogniaResult<Vec<JobRecord>> {
+        if let Some(parent) = db_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let url = format!("sqlite:{}?mode=rwc", db_path.display());
+        let pool = SqlitePoolOptions::new()
+            .max_connections(2)
+            .connect(&url)
+            .await
+            .map_err(|e| CogniaError::Internal(format!("Failed to open job database: {}", e)))?;
+
+        sqlx::query("PRAGMA busy_timeout = 5000")
+            .execute(&pool)
+            .await
+            .map_err(|e| CogniaError::Internal(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS jobs (
+                id TEXT PRIMARY KEY,
+                kind TEXT NOT NULL,
+                title TEXT NOT NULL,
+                params TEXT NOT NULL,
+                status TEXT NOT NULL,
+                progress REAL,
+                message TEXT,
+                error TEXT,
+                started_at TEXT NOT NULL,
+                finished_at TEXT,
+                resumable INTEGER NOT NULL DEFAULT 0
+            );
+            CREATE INDEX IF NOT EXISTS idx_jobs_started ON jobs(started_at);
+            CREATE INDEX IF NOT EXISTS idx_jobs_status ON jobs(status);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| CogniaError::Internal(format!("Failed to create jobs table: {}", e)))?;
+
+        // Jobs this process is running right now must not be swept up
+        let active_ids: Vec<String> = self.active_jobs().keys().cloned().collect();
+        let stale: Vec<JobRow> = sqlx::query_as("SELECT * FROM jobs WHERE status = 'running'")
+            .fetch_all(&pool)
+            .await
+            .map_err(|e| CogniaError::Internal(e.to_string()))?;
+
+        let now = chrono::Utc::now().to_rfc3339();
+        let mut interrupted = Vec::new();
+        for row in stale {
+            if active_ids.contains(&row.id) {
+                continue;
+            }
+            let Some(mut record) = row.into_record() else {
+                continue;
+            };
+            record.status = JobStatus::Interrupted;
+            record.finished_at = Some(now.clone());
+            record.resumable = record.kind.is_resumable();
+            Self::write_record(&pool, &record).await?;
+            interrupted.push(record);
+        }
+
+        sqlx::query(
+            "DELETE FROM jobs WHERE status != 'running' AND id NOT IN \
+             (SELECT id FROM jobs ORDER BY started_at DESC LIMIT ?)",
+        )
+        .bind(MAX_JOB_HISTORY)
+        .execute(&pool)
+        .await
+        .map_err(|e| CogniaError::Internal(e.to_string()))?;
+
+        // Persist jobs that started before the store was available
+        let pending: Vec<JobRecord> = self
+            .active_jobs()
+            .values()
+            .map(|job| job.record.clone())
+            .collect();
+        for record in &pending {
+            Self::write_record(&pool, record).await?;
+        }
+
+        *self.pool.write().await = Some(pool);
+        Ok(interrupted)
+    }
+
+    fn active_jobs(&self) -> std::sync::RwLockReadGuard<'_, HashMap<String, ActiveJob>> {
+        self.active.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn active_jobs_mut(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<String, ActiveJob>> {
+        self.active.write().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Receive lifecycle events (e.g. to forward them to the frontend)
+    pub fn set_listener(&self, listener: impl Fn(JobEvent) + Send + Sync + 'static) {
+        if let Ok(mut guard) = self.listener.write() {
+            *guard = Some(Arc::new(listener));
+        }
+    }
+
+    fn emit(&self, lifecycle: JobLifecycle, job: &JobRecord) {
+        let listener = self.listener.read().ok().and_then(|guard| guard.clone());
+        if let Some(listener) = listener {
+            listener(JobEvent {
+                lifecycle,
+                job: job.clone(),
+            });
+        }
+    }
+
+    async fn write_record(pool: &SqlitePool, record: &JobRecord) -> CogniaResult<()> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO jobs
+                (id, kind, title, params, status, progress, message, error,
+                 started_at, finished_at, resumable)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&record.id)
+        .bind(record.kind.as_str())
+        .bind(&record.title)
+        .bind(record.params.to_string())
+        .bind(record.status.as_str())
+        .bind(record.progress)
+        .bind(&record.message)
+        .bind(&record.error)
+        .bind(&record.started_at)
+        .bind(&record.finished_at)
+        .bind(record.resumable)
+        .execute(pool)
+        .await
+        .map_err(|e| CogniaError::Internal(format!("Failed to persist job: {}", e)))?;
+        Ok(())
+    }
+
+    async fn persist(&self, record: &JobRecord) {
+        if let Some(pool) = self.pool.read().await.as_ref() {
+            if let Err(e) = Self::write_record(pool, record).await {
+                log::warn!("{}", e);
+            }
+        }
+    }
+
+    /// Register a running job. Pass `cancel` to reuse a command's existing
+    /// cancellation token so `jobs_cancel` reaches the operation.
+    pub async fn start(
+        self: &Arc<Self>,
+        kind: JobKind,
+        title: impl Into<String>,
+        params: serde_json::Value,
+        cancel: Option<Arc<AtomicBool>>,
+    ) -> JobHandle {
+        let record = JobRecord {
+            id: uuid::Uuid::new_v4().to_string(),
+            kind,
+            title: title.into(),
+            params,
+            status: JobStatus::Running,
+            progress: None,
+            message: None,
+            error: None,
+            started_at: chrono::Utc::now().to_rfc3339(),
+            finished_at: None,
+            resumable: false,
+        };
+        let cancel = cancel.unwrap_or_default();
+
+        self.active_jobs_mut().insert(
+            record.id.clone(),
+            ActiveJob {
+                record: record.clone(),
+                cancel: cancel.clone(),
+            },
+        );
+        self.persist(&record).await;
+        self.emit(JobLifecycle::Started, &record);
+
+        JobHandle {
+            id: record.id,
+            cancel,
+            queue: self.clone(),
+            finished: false,
+        }
+    }
+
+    fn update_progress(&self, id: &str, progress: Option<f64>, message: Option<String>) {
+        let record = {
+            let mut active = self.active_jobs_mut();
+            let Some(job) = active.get_mut(id) else {
+                return;
+            };
+            if let Some(progress) = progress {
+                job.record.progress = Some(progress.clamp(0.0, 100.0));
+            }
+            if message.is_some() {
+                job.record.message = message;
+            }
+            job.record.clone()
+        };
+        // Progress stays in memory; the row is rewritten when the job ends
+        self.emit(JobLifecycle::Progress, &record);
+    }
+
+    async fn finish(&self, id: &str, status: JobStatus, error: Option<String>) {
+        let Some(job) = self.active_jobs_mut().remove(id) else {
+            return;
+        };
+        let mut record = job.record;
+        record.status = status;
+        record.error = error;
+        record.finished_at = Some(chrono::Utc::now().to_rfc3339());
+        record.resumable = status == JobStatus::Interrupted && record.kind.is_resumable();
+        if status == JobStatus::Completed {
+            record.progress = Some(100.0);
+        }
+        self.persist(&record).await;
+        self.emit(JobLifecycle::Finished, &record);
+    }
+
+    /// Request cancellation of a running job. Returns whether it was running.
+    pub fn cancel(&self, id: &str) -> bool {
+        match self.active_jobs().get(id) {
+            Some(job) => {
+                job.cancel.store(true, Ordering::SeqCst);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub async fn get(&self, id: &str) -> CogniaResult<Option<JobRecord>> {
+        if let Some(job) = self.active_jobs().get(id) {
+            return Ok(Some(job.record.clone()));
+        }
+        let Some(pool) = self.pool.read().await.clone() else {
+            return Ok(None);
+        };
+        let row: Option<JobRow> = sqlx::query_as("SELECT * FROM jobs WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&pool)
+            .await
+            .map_err(|e| CogniaError::Internal(e.to_string()))?;
+        Ok(row.and_then(JobRow::into_record))
+    }
+
+    /// Running jobs first, then history, newest first
+    pub async fn list(&self, query: &JobQuery) -> CogniaResult<Vec<JobRecord>> {
+        let matches = |record: &JobRecord| {
+            !matches!(query.kind, Some(kind) if record.kind != kind)
+                && !matches!(query.status, Some(status) if record.status != status)
+        };
+
+        let mut jobs: Vec<JobRecord> = self
+            .active_jobs()
+            .values()
+            .map(|job| job.record.clone())
+            .filter(|record| matches(record))
+            .collect();
+        jobs.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+
+        if let Some(pool) = self.pool.read().await.clone() {
+            let rows: Vec<JobRow> = sqlx::query_as(
+                "SELECT * FROM jobs WHERE status != 'running' ORDER BY started_at DESC",
+            )
+            .fetch_all(&pool)
+            .await
+            .map_err(|e| CogniaError::Internal(e.to_string()))?;
+            jobs.extend(
+                rows.into_iter()
+                    .filter_map(JobRow::into_record)
+                    .filter(|record| matches(record)),
+            );
+        }
+
+        if let Some(limit) = query.limit {
+            jobs.truncate(limit);
+        }
+        Ok(jobs)
+    }
+}
+
+/// Handle held by the code running a job
+pub struct JobHandle {
+    id: String,
+    cancel: Arc<AtomicBool>,
+    queue: Arc<OperationQueue>,
+    finished: bool,
+}
+
+impl JobHandle {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::SeqCst)
+    }
+
+    /// The job's cancellation flag, for work loops that poll a shared flag
+    pub fn cancel_flag(&self) -> Arc<AtomicBool> {
+        self.cancel.clone()
+    }
+
+    pub fn progress(&self, progress: Option<f64>, message: Option<String>) {
+        self.queue.update_progress(&self.id, progress, message);
+    }
+
+    /// Record the outcome of the operation. Errors after a cancel request
+    /// are recorded as cancelled.
+    pub async fn finish<T>(mut self, result: &Result<T, String>) {
+        let (status, error) = match result {
+            Ok(_) => (JobStatus::Completed, None),
+            Err(_) if self.is_cancelled() => (JobStatus::Cancelled, None),
+            Err(e) => (JobStatus::Failed, Some(e.clone())),
+        };
+        self.finished = true;
+        self.queue.finish(&self.id, status, error).await;
+    }
+}
+
+/// Closes out a job whose task panicked, or whose future was dropped,
+/// before it called `finish`, so it does not stay running until restart.
+impl Drop for JobHandle {
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+        let (status, error) = if std::thread::panicking() {
+            (
+                JobStatus::Failed,
+                Some("The operation panicked".to_string()),
+            )
+        } else {
+            (JobStatus::Interrupted, None)
+        };
+        let queue = self.queue.clone();
+        let id = self.id.clone();
+        match tokio::runtime::Handle::try_current() {
+            Ok(runtime) => {
+                runtime.spawn(async move { queue.finish(&id, status, error).await });
+            }
+            Err(_) => {
+                queue.active_jobs_mut().remove(&id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_job_lifecycle_is_persisted() {
+        let dir = tempdir().unwrap();
+        let queue = Arc::new(OperationQueue::new());
+        queue
+            .attach_store(&dir.path().join("jobs.db"))
+            .await
+            .unwrap();
+
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = events.clone();
+        queue.set_listener(move |event| sink.lock().unwrap().push(event.lifecycle));
+
+        let job = queue
+            .start(
+                JobKind::EnvInstall,
+                "Install node 20",
+                serde_json::json!({ "envType": "node", "version": "20" }),
+                None,
+            )
+            .await;
+        let id = job.id().to_string();
+        job.progress(Some(40.0), Some("Downloading".into()));
+
+        let running = queue.get(&id).await.unwrap().unwrap();
+        assert_eq!(running.status, JobStatus::Running);
+        assert_eq!(running.progress, Some(40.0));
+
+        job.finish(&Ok::<(), String>(())).await;
+        let done = queue.get(&id).await.unwrap().unwrap();
+        assert_eq!(done.status, JobStatus::Completed);
+        assert_eq!(done.params["version"], "20");
+        assert!(done.finished_at.is_some());
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                JobLifecycle::Started,
+                JobLifecycle::Progress,
+                JobLifecycle::Finished
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancel_uses_shared_token() {
+        let queue = Arc::new(OperationQueue::new());
+        let token = Arc::new(AtomicBool::new(false));
+        let job = queue
+            .start(
+                JobKind::BatchInstall,
+                "Install 3 packages",
+                serde_json::Value::Null,
+                Some(token.clone()),
+            )
+            .await;
+        let id = job.id().to_string();
+
+        assert!(queue.cancel(&id));
+        assert!(token.load(Ordering::SeqCst));
+
+        job.finish(&Err::<(), _>("aborted".to_string())).await;
+        assert!(!queue.cancel(&id));
+        // No store attached: finished jobs are not retained
+        assert!(queue.get(&id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_panicking_job_is_recorded_as_failed() {
+        let queue = Arc::new(OperationQueue::new());
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        queue.set_listener(move |event| {
+            if event.lifecycle == JobLifecycle::Finished {
+                let _ = tx.send(event.job);
+            }
+        });
+
+        let job = queue
+            .start(
+                JobKind::WslExport,
+                "Export Ubuntu",
+                serde_json::Value::Null,
+                None,
+            )
+            .await;
+        let id = job.id().to_string();
+        let task = tokio::spawn(async move {
+            let _job = job;
+            panic!("export crashed");
+        });
+        assert!(task.await.is_err());
+
+        let finished = tokio::time::timeout(std::time::Duration::from_secs(5), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(finished.id, id);
+        assert_eq!(finished.status, JobStatus::Failed);
+        assert!(!queue.cancel(&id));
+    }
+
+    #[tokio::test]
+    async fn test_running_jobs_are_interrupted_on_next_start() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("jobs.db");
+
+        let first = Arc::new(OperationQueue::new());
+        first.attach_store(&db_path).await.unwrap();
+        let migrate = first
+            .start(
+                JobKind::CacheMigrate,
+                "Migrate cache",
+                serde_json::json!({ "destination": "/mnt/cache" }),
+                None,
+            )
+            .await;
+        let export = first
+            .start(
+                JobKind::WslExport,
+                "Export Ubuntu",
+                serde_json::Value::Null,
+                None,
+            )
+            .await;
+        // Simulate the app exiting without finishing either job; destructors
+        // do not run when the process exits
+        let (migrate_id, export_id) = (migrate.id().to_string(), export.id().to_string());
+        std::mem::forget((migrate, export, first));
+
+        let second = Arc::new(OperationQueue::new());
+        let interrupted = second.attach_store(&db_path).await.unwrap();
+        assert_eq!(interrupted.len(), 2);
+
+        let migrate = second.get(&migrate_id).await.unwrap().unwrap();
+        assert_eq!(migrate.status, JobStatus::Interrupted);
+        assert!(migrate.resumable);
+        let export = second.get(&export_id).await.unwrap().unwrap();
+        assert_eq!(export.status, JobStatus::Interrupted);
+        assert!(!export.resumable);
+
+        let listed = second
+            .list(&JobQuery {
+                status: Some(JobStatus::Interrupted),
+                kind: Some(JobKind::CacheMigrate),
+                limit: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, migrate_id);
+    }
+}
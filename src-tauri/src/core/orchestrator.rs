@@ -6,10 +6,12 @@
 };
 use crate::resolver::{Dependency, Package, Resolver, Version};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::RwLock;
+use tokio::sync::{mpsc, OnceCell, RwLock, Semaphore};
 use tokio::time::sleep;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -448,6 +450,444 @@ async fn get_installed_version(
     }
 }
 
+/// How often a running batch checks whether it was cancelled as a whole
+const BATCH_CANCEL_POLL: Duration = Duration::from_millis(200);
+
+/// One environment to install as part of a batch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvInstallBatchRequest {
+    pub env_type: String,
+    pub version: String,
+    #[serde(default)]
+    pub provider_id: Option<String>,
+}
+
+impl EnvInstallBatchRequest {
+    /// Same key `env_install_cancel` uses, so one install of a batch can be
+    /// cancelled on its own
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.env_type, self.version)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnvBatchItemState {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl EnvBatchItemState {
+    pub fn is_terminal(self) -> bool {
+        matches!(self, Self::Completed | Self::Failed | Self::Cancelled)
+    }
+}
+
+/// Progress of one install plus the overall batch percentage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvInstallBatchProgress {
+    pub batch_id: String,
+    pub key: String,
+    pub state: EnvBatchItemState,
+    pub progress: f32,
+    pub overall_progress: f32,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvInstallBatchItem {
+    pub key: String,
+    pub request: EnvInstallBatchRequest,
+    pub state: EnvBatchItemState,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvInstallBatchResult {
+    pub batch_id: String,
+    pub items: Vec<EnvInstallBatchItem>,
+    /// The failed requests, ready to be passed back to retry just those
+    pub retry_requests: Vec<EnvInstallBatchRequest>,
+    /// Set when regenerating shims after the installs failed
+    pub shim_error: Option<String>,
+}
+
+impl EnvInstallBatchResult {
+    pub fn new(batch_id: String, items: Vec<EnvInstallBatchItem>) -> Self {
+        let retry_requests = items
+            .iter()
+            .filter(|item| item.state == EnvBatchItemState::Failed)
+            .map(|item| item.request.clone())
+            .collect();
+        Self {
+            batch_id,
+            items,
+            retry_requests,
+            shim_error: None,
+        }
+    }
+
+    pub fn count(&self, state: EnvBatchItemState) -> usize {
+        self.items.iter().filter(|item| item.state == state).count()
+    }
+}
+
+/// Something an install needs from the host before it can run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InstallPrerequisite {
+    /// A C compiler that can build a program, and `make`, for providers
+    /// that build from source
+    BuildTools,
+    /// Development files of a library, by `pkg-config` name
+    Library(&'static str),
+}
+
+/// Program compiled to prove the toolchain and the C library headers work
+const BUILD_TOOLS_PROBE: &str = "#include <stdio.h>\nint main(void) { return 0; }\n";
+
+impl InstallPrerequisite {
+    /// What a provider needs before it can install. Libraries shared by
+    /// several providers, like OpenSSL, are checked once per batch.
+    pub fn for_provider(provider_id: &str) -> &'static [InstallPrerequisite] {
+        if cfg!(windows) {
+            return &[];
+        }
+        match provider_id {
+            "pyenv" => &[
+                Self::BuildTools,
+                Self::Library("openssl"),
+                Self::Library("zlib"),
+                Self::Library("libffi"),
+            ],
+            "rbenv" => &[
+                Self::BuildTools,
+                Self::Library("openssl"),
+                Self::Library("yaml-0.1"),
+            ],
+            "phpbrew" => &[
+                Self::BuildTools,
+                Self::Library("openssl"),
+                Self::Library("libxml-2.0"),
+            ],
+            _ => &[],
+        }
+    }
+
+    async fn check(self) -> Result<(), String> {
+        match self {
+            Self::BuildTools => check_build_tools().await,
+            Self::Library(name) => check_library(name).await,
+        }
+    }
+}
+
+/// Compile a trivial program with the first C compiler on PATH, so a
+/// compiler without libc headers is caught before a long source build
+async fn check_build_tools() -> Result<(), String> {
+    use crate::platform::process::{self, ProcessOptions};
+
+    let mut missing = Vec::new();
+    let mut compiler = None;
+    for candidate in ["cc", "gcc", "clang"] {
+        if let Some(path) = process::which(candidate).await {
+            compiler = Some(path);
+            break;
+        }
+    }
+    if process::which("make").await.is_none() {
+        missing.push("make");
+    }
+    let Some(compiler) = compiler else {
+        missing.insert(0, "a C compiler");
+        return Err(format!(
+            "Missing build tools needed to compile from source: {}",
+            missing.join(", ")
+        ));
+    };
+
+    let dir = tempfile::tempdir().map_err(|e| e.to_string())?;
+    let output = dir.path().join("probe");
+    let output = output.to_string_lossy();
+    let options = ProcessOptions::new()
+        .with_stdin(BUILD_TOOLS_PROBE)
+        .with_timeout(Duration::from_secs(60));
+    match process::execute(&compiler, &["-x", "c", "-", "-o", &output], Some(options)).await {
+        Ok(out) if out.success => {}
+        Ok(out) => {
+            return Err(format!(
+                "C compiler {} cannot build programs (are the C library headers installed?): {}",
+                compiler,
+                out.stderr.trim()
+            ))
+        }
+        Err(e) => return Err(format!("Failed to run C compiler {}: {}", compiler, e)),
+    }
+
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Missing build tools needed to compile from source: {}",
+            missing.join(", ")
+        ))
+    }
+}
+
+/// Look a library's development files up with `pkg-config`. Only Linux
+/// keeps them where `pkg-config` reliably finds them, and without
+/// `pkg-config` there is nothing to go on, so both pass.
+async fn check_library(name: &str) -> Result<(), String> {
+    use crate::platform::process;
+
+    if !cfg!(target_os = "linux") || process::which("pkg-config").await.is_none() {
+        return Ok(());
+    }
+    match process::execute("pkg-config", &["--exists", name], None).await {
+        Ok(out) if !out.success => Err(format!(
+            "Missing development files for {} needed to compile from source",
+            name
+        )),
+        _ => Ok(()),
+    }
+}
+
+type PrerequisiteCheck = Arc<OnceCell<Result<(), String>>>;
+
+/// Prerequisite checks shared by the installs of a batch, each run at most once
+#[derive(Default)]
+pub struct SharedPrerequisites {
+    checks: std::sync::Mutex<HashMap<InstallPrerequisite, PrerequisiteCheck>>,
+}
+
+impl SharedPrerequisites {
+    pub async fn ensure(&self, prerequisite: InstallPrerequisite) -> Result<(), String> {
+        self.ensure_with(prerequisite, || prerequisite.check())
+            .await
+    }
+
+    async fn ensure_with<F, Fut>(
+        &self,
+        prerequisite: InstallPrerequisite,
+        check: F,
+    ) -> Result<(), String>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<(), String>>,
+    {
+        let cell = self
+            .checks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(prerequisite)
+            .or_default()
+            .clone();
+        cell.get_or_init(check).await.clone()
+    }
+}
+
+struct BatchItemUpdate {
+    key: String,
+    state: EnvBatchItemState,
+    progress: f32,
+    message: Option<String>,
+}
+
+/// Reports one install's progress into its batch
+#[derive(Clone)]
+pub struct BatchItemReporter {
+    key: String,
+    tx: mpsc::UnboundedSender<BatchItemUpdate>,
+}
+
+impl BatchItemReporter {
+    pub fn report(&self, progress: f32, message: Option<String>) {
+        self.send(EnvBatchItemState::Running, progress, message);
+    }
+
+    fn send(&self, state: EnvBatchItemState, progress: f32, message: Option<String>) {
+        let _ = self.tx.send(BatchItemUpdate {
+            key: self.key.clone(),
+            state,
+            progress,
+            message,
+        });
+    }
+}
+
+/// Latest state of every install, used to compute the overall percentage
+struct BatchTracker {
+    items: HashMap<String, (EnvBatchItemState, f32)>,
+}
+
+impl BatchTracker {
+    fn new(keys: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            items: keys
+                .into_iter()
+                .map(|key| (key, (EnvBatchItemState::Queued, 0.0)))
+                .collect(),
+        }
+    }
+
+    /// Record an update and return the item's and the batch's percentage.
+    /// Finished installs count as complete whatever their outcome.
+    fn apply(&mut self, update: &BatchItemUpdate) -> (f32, f32) {
+        let progress = if update.state.is_terminal() {
+            100.0
+        } else {
+            update.progress.clamp(0.0, 100.0)
+        };
+        if let Some(entry) = self.items.get_mut(&update.key) {
+            // Late provider events must not reopen a finished install
+            if !entry.0.is_terminal() {
+                *entry = (update.state, progress);
+            }
+        }
+        let total: f32 = self.items.values().map(|(_, p)| p).sum();
+        let overall = if self.items.is_empty() {
+            100.0
+        } else {
+            total / self.items.len() as f32
+        };
+        (progress, overall)
+    }
+}
+
+/// Several environment installs run together. Each install has its own
+/// cancel token; cancelling the batch token cancels all of them.
+pub struct EnvInstallBatch {
+    id: String,
+    requests: Vec<EnvInstallBatchRequest>,
+    tokens: HashMap<String, Arc<AtomicBool>>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl EnvInstallBatch {
+    /// Identical requests collapse into one
+    pub fn new(requests: Vec<EnvInstallBatchRequest>) -> Self {
+        let mut seen = HashSet::new();
+        let requests: Vec<_> = requests
+            .into_iter()
+            .filter(|request| seen.insert(request.key()))
+            .collect();
+        let tokens = requests
+            .iter()
+            .map(|request| (request.key(), Arc::new(AtomicBool::new(false))))
+            .collect();
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            requests,
+            tokens,
+            cancel: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn requests(&self) -> &[EnvInstallBatchRequest] {
+        &self.requests
+    }
+
+    pub fn cancel_token(&self) -> Arc<AtomicBool> {
+        self.cancel.clone()
+    }
+
+    pub fn item_token(&self, key: &str) -> Option<Arc<AtomicBool>> {
+        self.tokens.get(key).cloned()
+    }
+
+    /// Run the installs, at most `max_concurrent` at a time in request order.
+    /// `on_progress` sees every state change and progress report.
+    pub async fn run<F, Fut, P>(
+        &self,
+        max_concurrent: usize,
+        install: F,
+        mut on_progress: P,
+    ) -> Vec<EnvInstallBatchItem>
+    where
+        F: Fn(EnvInstallBatchRequest, Arc<AtomicBool>, BatchItemReporter) -> Fut,
+        Fut: Future<Output = Result<(), String>>,
+        P: FnMut(&EnvInstallBatchProgress),
+    {
+        let semaphore = Semaphore::new(max_concurrent.max(1));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut tracker = BatchTracker::new(self.requests.iter().map(|r| r.key()));
+
+        let installs = futures::future::join_all(self.requests.iter().map(|request| {
+            let reporter = BatchItemReporter {
+                key: request.key(),
+                tx: tx.clone(),
+            };
+            let token = self.tokens[&reporter.key].clone();
+            let (semaphore, install) = (&semaphore, &install);
+            async move {
+                reporter.send(EnvBatchItemState::Queued, 0.0, None);
+                let _permit = semaphore.acquire().await;
+                let (state, error) = if token.load(Ordering::SeqCst) {
+                    (EnvBatchItemState::Cancelled, None)
+                } else {
+                    reporter.send(EnvBatchItemState::Running, 0.0, None);
+                    match install(request.clone(), token.clone(), reporter.clone()).await {
+                        Ok(()) => (EnvBatchItemState::Completed, None),
+                        Err(e) if token.load(Ordering::SeqCst) => {
+                            (EnvBatchItemState::Cancelled, Some(e))
+                        }
+                        Err(e) => (EnvBatchItemState::Failed, Some(e)),
+                    }
+                };
+                reporter.send(state, 100.0, error.clone());
+                EnvInstallBatchItem {
+                    key: reporter.key.clone(),
+                    request: request.clone(),
+                    state,
+                    error,
+                }
+            }
+        }));
+        // Only the installs hold senders now, so the channel closes with them
+        drop(tx);
+
+        let pump = async {
+            let mut cancel_poll = tokio::time::interval(BATCH_CANCEL_POLL);
+            loop {
+                tokio::select! {
+                    update = rx.recv() => {
+                        let Some(update) = update else { break };
+                        let (progress, overall_progress) = tracker.apply(&update);
+                        on_progress(&EnvInstallBatchProgress {
+                            batch_id: self.id.clone(),
+                            key: update.key,
+                            state: update.state,
+                            progress,
+                            overall_progress,
+                            message: update.message,
+                        });
+                    }
+                    _ = cancel_poll.tick() => {
+                        if self.cancel.load(Ordering::SeqCst) {
+                            for token in self.tokens.values() {
+                                token.store(true, Ordering::SeqCst);
+                            }
+                        }
+                    }
+                }
+            }
+        };
+
+        let (items, ()) = tokio::join!(installs, pump);
+        items
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -591,4 +1031,153 @@ fn test_orchestrator_constants() {
         assert_eq!(Orchestrator::VERSION_VERIFY_RETRIES, 3);
         assert_eq!(Orchestrator::VERSION_VERIFY_DELAY_MS, 500);
     }
+
+    fn batch_request(env_type: &str, version: &str) -> EnvInstallBatchRequest {
+        EnvInstallBatchRequest {
+            env_type: env_type.into(),
+            version: version.into(),
+            provider_id: None,
+        }
+    }
+
+    #[test]
+    fn test_env_batch_dedupes_requests() {
+        let batch = EnvInstallBatch::new(vec![
+            batch_request("node", "20"),
+            batch_request("python", "3.12"),
+            batch_request("node", "20"),
+        ]);
+        assert_eq!(batch.requests().len(), 2);
+        assert!(batch.item_token("node:20").is_some());
+        assert!(batch.item_token("rust:1.80").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_env_batch_bounds_concurrency_and_reports_failures() {
+        use std::sync::atomic::AtomicUsize;
+
+        let batch = EnvInstallBatch::new(vec![
+            batch_request("node", "20"),
+            batch_request("python", "3.12"),
+            batch_request("rust", "1.80"),
+            batch_request("go", "1.22"),
+        ]);
+        let running = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let mut overall = Vec::new();
+
+        let items = batch
+            .run(
+                2,
+                |request, _token, reporter| {
+                    let (running, peak) = (&running, &peak);
+                    async move {
+                        let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                        peak.fetch_max(now, Ordering::SeqCst);
+                        reporter.report(50.0, None);
+                        sleep(Duration::from_millis(20)).await;
+                        running.fetch_sub(1, Ordering::SeqCst);
+                        if request.env_type == "rust" {
+                            Err("download failed".to_string())
+                        } else {
+                            Ok(())
+                        }
+                    }
+                },
+                |progress| overall.push(progress.overall_progress),
+            )
+            .await;
+
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+        assert_eq!(overall.last().copied(), Some(100.0));
+        assert!(overall.windows(2).all(|w| w[0] <= w[1]));
+
+        let result = EnvInstallBatchResult::new(batch.id().to_string(), items);
+        assert_eq!(result.count(EnvBatchItemState::Completed), 3);
+        assert_eq!(result.retry_requests, vec![batch_request("rust", "1.80")]);
+    }
+
+    #[tokio::test]
+    async fn test_env_batch_cancels_one_install_only() {
+        let batch = EnvInstallBatch::new(vec![
+            batch_request("node", "20"),
+            batch_request("python", "3.12"),
+        ]);
+        batch
+            .item_token("python:3.12")
+            .unwrap()
+            .store(true, Ordering::SeqCst);
+
+        let items = batch
+            .run(1, |_request, _token, _reporter| async { Ok(()) }, |_| {})
+            .await;
+
+        assert_eq!(items[0].state, EnvBatchItemState::Completed);
+        assert_eq!(items[1].state, EnvBatchItemState::Cancelled);
+        let result = EnvInstallBatchResult::new(batch.id().to_string(), items);
+        assert!(result.retry_requests.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_shared_prerequisites_check_once() {
+        use std::sync::atomic::AtomicUsize;
+
+        let prerequisites = SharedPrerequisites::default();
+        let checks = AtomicUsize::new(0);
+        let counter = &checks;
+        let check = || async move {
+            counter.fetch_add(1, Ordering::SeqCst);
+            sleep(Duration::from_millis(10)).await;
+            Err("missing make".to_string())
+        };
+
+        let (first, second) = tokio::join!(
+            prerequisites.ensure_with(InstallPrerequisite::BuildTools, check),
+            prerequisites.ensure_with(InstallPrerequisite::BuildTools, check),
+        );
+        assert_eq!(first, Err("missing make".to_string()));
+        assert_eq!(first, second);
+        assert_eq!(checks.load(Ordering::SeqCst), 1);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_source_builds_share_prerequisites() {
+        let python = InstallPrerequisite::for_provider("pyenv");
+        let ruby = InstallPrerequisite::for_provider("rbenv");
+        let shared: Vec<_> = python.iter().filter(|p| ruby.contains(p)).collect();
+        assert_eq!(
+            shared,
+            [
+                &InstallPrerequisite::BuildTools,
+                &InstallPrerequisite::Library("openssl")
+            ]
+        );
+        assert!(InstallPrerequisite::for_provider("fnm").is_empty());
+    }
+
+    #[test]
+    fn test_batch_tracker_keeps_finished_items_complete() {
+        let mut tracker = BatchTracker::new(["a".to_string(), "b".to_string()]);
+        let update = |key: &str, state, progress| BatchItemUpdate {
+            key: key.to_string(),
+            state,
+            progress,
+            message: None,
+        };
+
+        assert_eq!(
+            tracker.apply(&update("a", EnvBatchItemState::Running, 40.0)),
+            (40.0, 20.0)
+        );
+        assert_eq!(
+            tracker.apply(&update("a", EnvBatchItemState::Failed, 0.0)),
+            (100.0, 50.0)
+        );
+        tracker.apply(&update("a", EnvBatchItemState::Running, 10.0));
+        assert_eq!(
+            tracker.apply(&update("b", EnvBatchItemState::Running, 50.0)),
+            (50.0, 75.0)
+        );
+    }
 }
@@ -0,0 +1,446 @@
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::env::EnvModifications;
+use crate::platform::fs;
+use crate::provider::InstallReceipt;
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::sync::Mutex;
+
+/// How long a try-prefix lives when the caller does not pick a TTL
+pub const DEFAULT_TRY_TTL_HOURS: u64 = 24;
+/// Longest TTL a try can ask for; a try is not meant to be kept
+pub const MAX_TRY_TTL_HOURS: u64 = 24 * 30;
+/// Version the providers report when they could not read the installed one
+const UNKNOWN_VERSION: &str = "unknown";
+static TRY_REGISTRY_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// A package installed into a throwaway prefix under `<root>/try/<id>`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageTry {
+    pub id: String,
+    pub provider: String,
+    pub name: String,
+    pub version: String,
+    pub prefix: PathBuf,
+    /// Directories to put on PATH to use the package
+    pub bin_dirs: Vec<PathBuf>,
+    /// Executables the package brought into `bin_dirs`
+    pub bin_paths: Vec<PathBuf>,
+    /// Variables the provider's runtime needs to find the prefix
+    #[serde(default)]
+    pub env_vars: HashMap<String, String>,
+    pub created_at: String,
+    pub expires_at: String,
+}
+
+impl PackageTry {
+    /// Describe an install that `install_to_prefix` just finished. The TTL
+    /// is capped at [`MAX_TRY_TTL_HOURS`].
+    pub fn new(
+        id: String,
+        receipt: &InstallReceipt,
+        prefix: &Path,
+        ttl_hours: u64,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let bin_dirs = bin_dirs(&receipt.provider, prefix);
+        let bin_paths = bin_dirs
+            .iter()
+            .flat_map(|dir| list_executables(&receipt.provider, dir))
+            .collect();
+
+        Self {
+            id,
+            provider: receipt.provider.clone(),
+            name: receipt.name.clone(),
+            version: receipt.version.clone(),
+            prefix: prefix.to_path_buf(),
+            bin_dirs,
+            bin_paths,
+            env_vars: activation_vars(&receipt.provider, prefix),
+            created_at: now.to_rfc3339(),
+            expires_at: (now + Duration::hours(ttl_hours.min(MAX_TRY_TTL_HOURS) as i64))
+                .to_rfc3339(),
+        }
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        DateTime::parse_from_rfc3339(&self.expires_at)
+            .map(|expires| expires <= now)
+            .unwrap_or(true)
+    }
+
+    /// `provider:name@version` to install the tried package for real; the
+    /// version is left out when the provider could not tell which it got
+    pub fn promote_spec(&self) -> String {
+        let version = self.version.trim();
+        if version.is_empty() || version == UNKNOWN_VERSION {
+            format!("{}:{}", self.provider, self.name)
+        } else {
+            format!("{}:{}@{}", self.provider, self.name, version)
+        }
+    }
+
+    /// Environment changes that make the tried package usable in a shell
+    pub fn env_modifications(&self) -> EnvModifications {
+        let mut modifications = EnvModifications::new();
+        for dir in &self.bin_dirs {
+            modifications = modifications.prepend_path(dir);
+        }
+        for (key, value) in &self.env_vars {
+            modifications = modifications.set_var(key, value);
+        }
+        modifications
+    }
+}
+
+/// Directory holding every try-prefix and the registry
+pub fn try_root(root_dir: &Path) -> PathBuf {
+    root_dir.join("try")
+}
+
+/// Prefix for a new try of `name`; the id is unique per try
+pub fn new_try_id(name: &str) -> String {
+    let slug: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    format!("{}-{}", slug.trim_matches('-'), &suffix[..8])
+}
+
+fn bin_dirs(provider: &str, prefix: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    // npm keeps its Windows global layout, with the .cmd shims at the top
+    if provider == "npm" && cfg!(windows) {
+        dirs.push(prefix.to_path_buf());
+    }
+    for name in ["bin", "Scripts"] {
+        let dir = prefix.join(name);
+        if dir.is_dir() {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+fn activation_vars(provider: &str, prefix: &Path) -> HashMap<String, String> {
+    let prefix_str = prefix.display().to_string();
+    let mut vars = HashMap::new();
+    match provider {
+        "pip" => {
+            vars.insert("VIRTUAL_ENV".to_string(), prefix_str);
+        }
+        "gem" => {
+            vars.insert("GEM_HOME".to_string(), prefix_str.clone());
+            vars.insert("GEM_PATH".to_string(), prefix_str);
+        }
+        "npm" => {
+            let modules = if cfg!(windows) {
+                prefix.join("node_modules")
+            } else {
+                prefix.join("lib").join("node_modules")
+            };
+            vars.insert("NODE_PATH".to_string(), modules.display().to_string());
+        }
+        _ => {}
+    }
+    vars
+}
+
+/// Files a virtualenv puts in `bin` that do not belong to the package
+const VENV_SCAFFOLDING: &[&str] = &["activate", "deactivate", "pip", "python"];
+
+fn list_executables(provider: &str, dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut executables: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && is_executable(path))
+        .filter(|path| {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_ascii_lowercase())
+                .unwrap_or_default();
+            provider != "pip"
+                || !VENV_SCAFFOLDING
+                    .iter()
+                    .any(|scaffold| name.starts_with(scaffold))
+        })
+        .collect();
+    executables.sort();
+    executables
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path)
+        .map(|meta| meta.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(windows)]
+fn is_executable(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+        .is_some_and(|ext| matches!(ext.as_str(), "exe" | "cmd" | "bat" | "ps1"))
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct TryRegistry {
+    tries: Vec<PackageTry>,
+}
+
+fn registry_path(root_dir: &Path) -> PathBuf {
+    try_root(root_dir).join("tries.json")
+}
+
+async fn load_registry(root_dir: &Path) -> CogniaResult<TryRegistry> {
+    Ok(fs::read_json_with_backup(registry_path(root_dir))
+        .await?
+        .into_value("Package try registry")
+        .unwrap_or_default())
+}
+
+async fn save_registry(root_dir: &Path, registry: &TryRegistry) -> CogniaResult<()> {
+    fs::atomic_write_json(registry_path(root_dir), registry, true).await?;
+    Ok(())
+}
+
+async fn remove_prefix(entry: &PackageTry) {
+    if fs::exists(&entry.prefix).await {
+        if let Err(e) = fs::remove_dir_all(&entry.prefix).await {
+            log::warn!("Failed to remove try prefix {:?}: {}", entry.prefix, e);
+        }
+    }
+}
+
+/// Record a finished try so it can be listed, promoted and expired
+pub async fn register_try(root_dir: &Path, entry: PackageTry) -> CogniaResult<()> {
+    let _guard = TRY_REGISTRY_LOCK.lock().await;
+    let mut registry = load_registry(root_dir).await?;
+    registry.tries.retain(|t| t.id != entry.id);
+    registry.tries.push(entry);
+    save_registry(root_dir, &registry).await
+}
+
+/// All registered tries, newest first
+pub async fn list_tries(root_dir: &Path) -> CogniaResult<Vec<PackageTry>> {
+    let mut tries = load_registry(root_dir).await?.tries;
+    tries.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(tries)
+}
+
+pub async fn get_try(root_dir: &Path, id: &str) -> CogniaResult<PackageTry> {
+    load_registry(root_dir)
+        .await?
+        .tries
+        .into_iter()
+        .find(|t| t.id == id)
+        .ok_or_else(|| CogniaError::PackageNotFound(format!("No package try with id {}", id)))
+}
+
+/// Unregister a try and delete its prefix. Returns false if `id` is unknown.
+pub async fn remove_try(root_dir: &Path, id: &str) -> CogniaResult<bool> {
+    let _guard = TRY_REGISTRY_LOCK.lock().await;
+    let mut registry = load_registry(root_dir).await?;
+    let Some(index) = registry.tries.iter().position(|t| t.id == id) else {
+        return Ok(false);
+    };
+    let entry = registry.tries.remove(index);
+    save_registry(root_dir, &registry).await?;
+    remove_prefix(&entry).await;
+    Ok(true)
+}
+
+/// Delete every try whose TTL has passed; run by the cache cleanup task.
+/// Prefixes that never made it into the registry, because the app quit
+/// mid-install or deleting them failed before, go once they are older than
+/// [`DEFAULT_TRY_TTL_HOURS`].
+pub async fn prune_expired_tries(
+    root_dir: &Path,
+    now: DateTime<Utc>,
+) -> CogniaResult<Vec<PackageTry>> {
+    let _guard = TRY_REGISTRY_LOCK.lock().await;
+    let mut registry = load_registry(root_dir).await?;
+    let (expired, kept): (Vec<_>, Vec<_>) =
+        registry.tries.into_iter().partition(|t| t.is_expired(now));
+
+    if !expired.is_empty() {
+        registry.tries = kept;
+        save_registry(root_dir, &registry).await?;
+        for entry in &expired {
+            remove_prefix(entry).await;
+        }
+    }
+    remove_orphaned_prefixes(root_dir, &registry, now).await;
+    Ok(expired)
+}
+
+async fn remove_orphaned_prefixes(root_dir: &Path, registry: &TryRegistry, now: DateTime<Utc>) {
+    let Ok(mut entries) = tokio::fs::read_dir(try_root(root_dir)).await else {
+        return;
+    };
+    let cutoff = now - Duration::hours(DEFAULT_TRY_TTL_HOURS as i64);
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if registry.tries.iter().any(|t| t.prefix == path) {
+            continue;
+        }
+        let Ok(meta) = entry.metadata().await else {
+            continue;
+        };
+        let modified = meta.modified().map(DateTime::<Utc>::from);
+        if !meta.is_dir() || !modified.is_ok_and(|m| m < cutoff) {
+            continue;
+        }
+        if let Err(e) = fs::remove_dir_all(&path).await {
+            log::warn!("Failed to remove orphaned try prefix {:?}: {}", path, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn receipt(provider: &str) -> InstallReceipt {
+        InstallReceipt {
+            name: "cowsay".into(),
+            version: "1.6.0".into(),
+            provider: provider.into(),
+            install_path: PathBuf::new(),
+            files: vec![],
+            installed_at: Utc::now().to_rfc3339(),
+        }
+    }
+
+    #[test]
+    fn test_new_try_id_is_path_safe() {
+        let id = new_try_id("@scope/pkg");
+        assert!(id.starts_with("scope-pkg-"));
+        assert_eq!(id.len(), "scope-pkg-".len() + 8);
+        assert_ne!(new_try_id("pkg"), new_try_id("pkg"));
+    }
+
+    #[test]
+    fn test_try_ttl_is_capped() {
+        let dir = tempfile::tempdir().unwrap();
+        let now = Utc::now();
+        let entry = PackageTry::new("a".into(), &receipt("cargo"), dir.path(), u64::MAX, now);
+
+        assert!(!entry.is_expired(now + Duration::hours(MAX_TRY_TTL_HOURS as i64 - 1)));
+        assert!(entry.is_expired(now + Duration::hours(MAX_TRY_TTL_HOURS as i64)));
+    }
+
+    #[test]
+    fn test_promote_spec_skips_unknown_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut entry = PackageTry::new("a".into(), &receipt("pip"), dir.path(), 1, Utc::now());
+        assert_eq!(entry.promote_spec(), "pip:cowsay@1.6.0");
+
+        entry.version = "unknown".into();
+        assert_eq!(entry.promote_spec(), "pip:cowsay");
+        entry.version = String::new();
+        assert_eq!(entry.promote_spec(), "pip:cowsay");
+    }
+
+    #[test]
+    fn test_try_expiry() {
+        let dir = tempfile::tempdir().unwrap();
+        let now = Utc::now();
+        let entry = PackageTry::new("a".into(), &receipt("cargo"), dir.path(), 2, now);
+
+        assert!(!entry.is_expired(now + Duration::hours(1)));
+        assert!(entry.is_expired(now + Duration::hours(2)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_try_discovers_bins_and_activation_env() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let bin = dir.path().join("bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        for name in ["cowsay", "pip3", "python3", "activate", "README"] {
+            let path = bin.join(name);
+            std::fs::write(&path, "").unwrap();
+            let mode = if name == "README" { 0o644 } else { 0o755 };
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).unwrap();
+        }
+
+        let entry = PackageTry::new("a".into(), &receipt("pip"), dir.path(), 1, Utc::now());
+        assert_eq!(entry.bin_dirs, vec![bin.clone()]);
+        assert_eq!(entry.bin_paths, vec![bin.join("cowsay")]);
+
+        let env = entry.env_modifications();
+        assert_eq!(env.path_prepend, vec![bin]);
+        assert_eq!(
+            env.set_variables.get("VIRTUAL_ENV"),
+            Some(&dir.path().display().to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_registry_roundtrip_and_prune() {
+        let root = tempfile::tempdir().unwrap();
+        let now = Utc::now();
+
+        let fresh_prefix = try_root(root.path()).join("fresh");
+        let stale_prefix = try_root(root.path()).join("stale");
+        std::fs::create_dir_all(&fresh_prefix).unwrap();
+        std::fs::create_dir_all(&stale_prefix).unwrap();
+
+        let fresh = PackageTry::new("fresh".into(), &receipt("npm"), &fresh_prefix, 24, now);
+        let stale = PackageTry::new(
+            "stale".into(),
+            &receipt("npm"),
+            &stale_prefix,
+            1,
+            now - Duration::hours(2),
+        );
+        register_try(root.path(), stale).await.unwrap();
+        register_try(root.path(), fresh).await.unwrap();
+
+        let listed = list_tries(root.path()).await.unwrap();
+        assert_eq!(
+            listed.iter().map(|t| t.id.as_str()).collect::<Vec<_>>(),
+            vec!["fresh", "stale"]
+        );
+        assert_eq!(get_try(root.path(), "fresh").await.unwrap().name, "cowsay");
+        assert!(get_try(root.path(), "missing").await.is_err());
+
+        let pruned = prune_expired_tries(root.path(), now).await.unwrap();
+        assert_eq!(pruned.len(), 1);
+        assert!(!stale_prefix.exists());
+        assert!(fresh_prefix.exists());
+
+        assert!(remove_try(root.path(), "fresh").await.unwrap());
+        assert!(!remove_try(root.path(), "fresh").await.unwrap());
+        assert!(!fresh_prefix.exists());
+        assert!(list_tries(root.path()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_prune_removes_orphaned_prefixes_after_grace() {
+        let root = tempfile::tempdir().unwrap();
+        let orphan = try_root(root.path()).join("orphan");
+        std::fs::create_dir_all(&orphan).unwrap();
+
+        prune_expired_tries(root.path(), Utc::now()).await.unwrap();
+        assert!(orphan.exists());
+
+        let later = Utc::now() + Duration::hours(DEFAULT_TRY_TTL_HOURS as i64 + 1);
+        prune_expired_tries(root.path(), later).await.unwrap();
+        assert!(!orphan.exists());
+    }
+}
@@ -0,0 +1,511 @@
+//! Which copy of a binary wins on PATH, and which copies it shadows.
+//!
+//! The GUI process inherits PATH from whatever launched it, which is often not
+//! what a freshly opened terminal sees, so reports are built both for the
+//! current process and for a login shell.
+
+use crate::platform::process::{self, ProcessOptions};
+use crate::provider::system::SystemDetectionConfig;
+use crate::provider::SystemEnvironmentType;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Binaries reported when the caller does not ask for specific ones.
+pub const DEFAULT_SHADOW_BINARIES: &[&str] = &[
+    "node", "npm", "npx", "python", "python3", "pip", "pip3", "cargo", "rustc", "go", "java",
+    "javac", "ruby", "gem", "php", "composer", "dotnet", "deno", "bun",
+];
+
+/// Shadowed copies beyond this many are listed but not executed.
+const MAX_PROBED_MATCHES: usize = 6;
+const VERSION_PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+const VERSION_CACHE_TTL: Duration = Duration::from_secs(600);
+#[cfg(not(windows))]
+const LOGIN_SHELL_TIMEOUT: Duration = Duration::from_secs(10);
+#[cfg(not(windows))]
+const LOGIN_PATH_MARKER: &str = "__COGNIA_PATH__";
+
+struct CachedVersion {
+    modified: Option<SystemTime>,
+    probed_at: Instant,
+    version: Option<String>,
+}
+
+/// `(binary_path, cwd)` -> last probe, invalidated when the binary changes.
+static VERSION_CACHE: Lazy<Mutex<HashMap<(String, String), CachedVersion>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Where a PATH value was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PathSource {
+    /// The PATH CogniaLauncher itself runs with
+    Process,
+    /// The PATH a new login shell ends up with after sourcing its profiles
+    LoginShell,
+}
+
+/// A copy of a binary found in one PATH entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShadowMatch {
+    /// Position of `dir` in the PATH list
+    pub path_index: usize,
+    pub dir: String,
+    pub binary_path: String,
+    pub version: Option<String>,
+    /// First match on PATH; every later match is shadowed by it
+    pub wins: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinaryShadowInfo {
+    pub name: String,
+    /// Matches in PATH order
+    pub matches: Vec<ShadowMatch>,
+    pub shadowed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathShadowContext {
+    pub source: PathSource,
+    /// Shell used to resolve the login PATH
+    pub shell: Option<String>,
+    pub path_entries: Vec<String>,
+    pub binaries: Vec<BinaryShadowInfo>,
+    /// Why the PATH could not be resolved; `binaries` is empty then
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathShadowReport {
+    pub process: PathShadowContext,
+    pub login_shell: PathShadowContext,
+    /// Binaries whose winning copy differs between the two PATHs
+    pub mismatched_binaries: Vec<String>,
+}
+
+/// Detection settings of the environment that owns `binary`, if any.
+pub fn detection_for_binary(binary: &str) -> Option<SystemDetectionConfig> {
+    SystemEnvironmentType::all()
+        .into_iter()
+        .map(|env_type| env_type.detection_config())
+        .find(|config| config.commands.contains(&binary))
+}
+
+/// `<binary> <version args>` from `cwd`, so shims resolve project pins the
+/// same way an interactive shell would. Results are cached until the binary
+/// is modified.
+pub async fn probe_binary_version(
+    binary_path: &str,
+    cwd: Option<&str>,
+    detection: Option<&SystemDetectionConfig>,
+) -> Option<String> {
+    let cache_key = (binary_path.to_string(), cwd.unwrap_or_default().to_string());
+    let modified = tokio::fs::metadata(binary_path)
+        .await
+        .and_then(|meta| meta.modified())
+        .ok();
+    if let Some(cached) = VERSION_CACHE.lock().unwrap().get(&cache_key) {
+        if cached.modified == modified && cached.probed_at.elapsed() < VERSION_CACHE_TTL {
+            return cached.version.clone();
+        }
+    }
+
+    let version = run_version_probe(binary_path, cwd, detection).await;
+    VERSION_CACHE.lock().unwrap().insert(
+        cache_key,
+        CachedVersion {
+            modified,
+            probed_at: Instant::now(),
+            version: version.clone(),
+        },
+    );
+    version
+}
+
+async fn run_version_probe(
+    binary_path: &str,
+    cwd: Option<&str>,
+    detection: Option<&SystemDetectionConfig>,
+) -> Option<String> {
+    let args = detection
+        .map(|config| config.version_args.clone())
+        .unwrap_or_else(|| vec!["--version"]);
+    let mut opts = ProcessOptions::new().with_timeout(VERSION_PROBE_TIMEOUT);
+    if let Some(cwd) = cwd.filter(|cwd| Path::new(cwd).is_dir()) {
+        opts = opts.with_cwd(cwd);
+    }
+    let output = process::execute(binary_path, &args, Some(opts))
+        .await
+        .ok()?;
+    extract_version(
+        &format!("{}\n{}", output.stdout, output.stderr),
+        detection.map(|config| config.version_pattern),
+    )
+}
+
+/// The captured version when `pattern` matches, otherwise the first
+/// non-empty line of the output.
+fn extract_version(text: &str, pattern: Option<&str>) -> Option<String> {
+    if let Some(captured) = pattern
+        .and_then(|pattern| regex::Regex::new(pattern).ok())
+        .and_then(|re| re.captures(text))
+        .and_then(|caps| caps.get(1))
+    {
+        return Some(captured.as_str().to_string());
+    }
+    text.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+/// Every copy of `binary` across `path_var`, without versions.
+pub fn find_matches(binary: &str, path_var: &OsStr) -> Vec<ShadowMatch> {
+    let mut matches: Vec<ShadowMatch> = Vec::new();
+    for (path_index, dir) in std::env::split_paths(path_var).enumerate() {
+        // A single directory is a valid PATH value on its own
+        let Some(binary_path) = process::which_all_in(binary, dir.as_os_str())
+            .into_iter()
+            .next()
+        else {
+            continue;
+        };
+        let binary_path = binary_path.display().to_string();
+        if matches.iter().any(|found| found.binary_path == binary_path) {
+            continue;
+        }
+        matches.push(ShadowMatch {
+            path_index,
+            dir: dir.display().to_string(),
+            binary_path,
+            version: None,
+            wins: matches.is_empty(),
+        });
+    }
+    matches
+}
+
+/// Shadow info for each of `binaries` across `path_var`, probing versions of
+/// the first few matches.
+pub async fn build_context(
+    source: PathSource,
+    shell: Option<String>,
+    path_var: &OsStr,
+    binaries: &[String],
+) -> PathShadowContext {
+    let mut infos = Vec::with_capacity(binaries.len());
+    for name in binaries {
+        let detection = detection_for_binary(name);
+        let mut matches = find_matches(name, path_var);
+        let probes = matches
+            .iter()
+            .take(MAX_PROBED_MATCHES)
+            .map(|found| probe_binary_version(&found.binary_path, None, detection.as_ref()));
+        let versions = futures::future::join_all(probes).await;
+        for (found, version) in matches.iter_mut().zip(versions) {
+            found.version = version;
+        }
+        infos.push(BinaryShadowInfo {
+            name: name.clone(),
+            shadowed: matches.len() > 1,
+            matches,
+        });
+    }
+
+    PathShadowContext {
+        source,
+        shell,
+        path_entries: std::env::split_paths(path_var)
+            .map(|entry| entry.display().to_string())
+            .collect(),
+        binaries: infos,
+        error: None,
+    }
+}
+
+/// Default binaries followed by `extra`, without duplicates.
+pub fn requested_binaries(extra: Option<Vec<String>>) -> Vec<String> {
+    let mut binaries: Vec<String> = DEFAULT_SHADOW_BINARIES
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    for name in extra.unwrap_or_default() {
+        let name = name.trim().to_string();
+        if !name.is_empty() && !binaries.contains(&name) {
+            binaries.push(name);
+        }
+    }
+    binaries
+}
+
+/// Binaries that resolve to a different file in the two contexts.
+pub fn mismatched_binaries(process: &PathShadowContext, login: &PathShadowContext) -> Vec<String> {
+    if login.error.is_some() {
+        return Vec::new();
+    }
+    let winner = |context: &PathShadowContext, name: &str| {
+        context
+            .binaries
+            .iter()
+            .find(|info| info.name == name)
+            .and_then(|info| info.matches.first())
+            .map(|found| found.binary_path.clone())
+    };
+    process
+        .binaries
+        .iter()
+        .filter(|info| winner(process, &info.name) != winner(login, &info.name))
+        .map(|info| info.name.clone())
+        .collect()
+}
+
+/// Extract the PATH printed between markers, skipping anything profiles echo.
+#[cfg(not(windows))]
+fn parse_login_shell_path(stdout: &str) -> Option<String> {
+    stdout
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(LOGIN_PATH_MARKER))
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty())
+}
+
+/// Program, arguments and display name used to print a login shell's PATH.
+#[cfg(not(windows))]
+fn login_shell_command() -> (String, Vec<String>, String) {
+    let shell = std::env::var("SHELL")
+        .ok()
+        .filter(|shell| !shell.is_empty())
+        .unwrap_or_else(|| "/bin/sh".to_string());
+    let name = Path::new(&shell)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| shell.clone());
+    let script = match name.as_str() {
+        "fish" => format!("printf '{}%s\\n' (string join : $PATH)", LOGIN_PATH_MARKER),
+        "nu" | "nushell" => format!(
+            "print $\"{}($env.PATH | str join (char esep))\"",
+            LOGIN_PATH_MARKER
+        ),
+        _ => format!("printf '{}%s\\n' \"$PATH\"", LOGIN_PATH_MARKER),
+    };
+    (
+        shell,
+        vec!["-l".to_string(), "-c".to_string(), script],
+        name,
+    )
+}
+
+/// PATH a new terminal starts with: the Machine entries followed by the User
+/// entries, with `%VAR%` references expanded from the persisted variables
+/// (User values win over Machine ones) and then the process environment.
+#[cfg(windows)]
+fn compose_registry_path(
+    machine: &[String],
+    user: &[String],
+    vars: &HashMap<String, String>,
+) -> String {
+    machine
+        .iter()
+        .chain(user)
+        .map(|entry| crate::platform::env::expand_path_with(entry.trim(), vars))
+        .filter(|entry| !entry.is_empty())
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// PATH as seen by a fresh login shell, with the shell that produced it.
+///
+/// On Windows a new terminal builds PATH from the Machine and User `Path`
+/// registry values, and any shell spawned from here would only inherit this
+/// process's stale PATH, so the registry is read directly.
+#[cfg(windows)]
+pub async fn login_shell_path() -> (String, Result<String, String>) {
+    use crate::platform::env::{get_persistent_path, list_persistent_vars, EnvVarScope};
+
+    let result = async {
+        let mut vars = HashMap::new();
+        for scope in [EnvVarScope::System, EnvVarScope::User] {
+            vars.extend(list_persistent_vars(scope).await?);
+        }
+        let machine = get_persistent_path(EnvVarScope::System).await?;
+        let user = get_persistent_path(EnvVarScope::User).await?;
+        Ok::<_, crate::error::CogniaError>(compose_registry_path(&machine, &user, &vars))
+    }
+    .await
+    .map_err(|e| format!("Failed to read PATH from the registry: {}", e));
+    ("registry".to_string(), result)
+}
+
+/// PATH as seen by a fresh login shell, with the shell that produced it.
+#[cfg(not(windows))]
+pub async fn login_shell_path() -> (String, Result<String, String>) {
+    let (program, args, name) = login_shell_command();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let opts = ProcessOptions::new().with_timeout(LOGIN_SHELL_TIMEOUT);
+    let result = match process::execute(&program, &args, Some(opts)).await {
+        Ok(output) => parse_login_shell_path(&output.stdout).ok_or_else(|| {
+            format!(
+                "{} did not print PATH (exit code {}): {}",
+                name,
+                output.exit_code,
+                output.stderr.trim()
+            )
+        }),
+        Err(e) => Err(format!("Failed to start {}: {}", name, e)),
+    };
+    (name, result)
+}
+
+/// Shadow report for the process PATH and a login shell PATH.
+pub async fn build_report(binaries: &[String]) -> PathShadowReport {
+    let process_path = std::env::var_os("PATH").unwrap_or_default();
+    let process = build_context(PathSource::Process, None, &process_path, binaries).await;
+
+    let (shell, login_path) = login_shell_path().await;
+    let login_shell = match login_path {
+        Ok(path) => {
+            build_context(
+                PathSource::LoginShell,
+                Some(shell),
+                OsStr::new(&path),
+                binaries,
+            )
+            .await
+        }
+        Err(error) => PathShadowContext {
+            source: PathSource::LoginShell,
+            shell: Some(shell),
+            path_entries: Vec::new(),
+            binaries: Vec::new(),
+            error: Some(error),
+        },
+    };
+
+    PathShadowReport {
+        mismatched_binaries: mismatched_binaries(&process, &login_shell),
+        process,
+        login_shell,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn touch_binary(dir: &Path, name: &str) {
+        #[cfg(windows)]
+        let name = format!("{}.exe", name);
+        std::fs::write(dir.join(name), b"").unwrap();
+    }
+
+    #[test]
+    fn test_find_matches_marks_first_as_winner() {
+        let first = tempdir().unwrap();
+        let empty = tempdir().unwrap();
+        let second = tempdir().unwrap();
+        touch_binary(first.path(), "tool");
+        touch_binary(second.path(), "tool");
+        let path_var = std::env::join_paths([first.path(), empty.path(), second.path()]).unwrap();
+
+        let matches = find_matches("tool", &path_var);
+        assert_eq!(matches.len(), 2);
+        assert!(matches[0].wins);
+        assert!(!matches[1].wins);
+        assert_eq!(matches[0].path_index, 0);
+        assert_eq!(matches[1].path_index, 2);
+        assert_eq!(matches[1].dir, second.path().display().to_string());
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_parse_login_shell_path_skips_profile_noise() {
+        let stdout = "Welcome back!\n__COGNIA_PATH__/usr/local/bin:/usr/bin\n";
+        assert_eq!(
+            parse_login_shell_path(stdout),
+            Some("/usr/local/bin:/usr/bin".to_string())
+        );
+        assert_eq!(parse_login_shell_path("no marker here"), None);
+        assert_eq!(parse_login_shell_path("__COGNIA_PATH__\n"), None);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_compose_registry_path_expands_machine_then_user() {
+        let vars = HashMap::from([
+            ("SystemRoot".to_string(), r"C:\Windows".to_string()),
+            ("TOOLS".to_string(), r"D:\tools".to_string()),
+        ]);
+        let machine = vec![r"%SystemRoot%\system32".to_string(), " ".to_string()];
+        let user = vec![r"%TOOLS%\bin".to_string()];
+        assert_eq!(
+            compose_registry_path(&machine, &user, &vars),
+            r"C:\Windows\system32;D:\tools\bin"
+        );
+    }
+
+    #[test]
+    fn test_extract_version_prefers_pattern() {
+        assert_eq!(
+            extract_version("v20.11.0\n", Some(r"v?(\d+\.\d+\.\d+)")),
+            Some("20.11.0".to_string())
+        );
+        assert_eq!(
+            extract_version("\n  pip 24.0 from /usr/lib\n", None),
+            Some("pip 24.0 from /usr/lib".to_string())
+        );
+    }
+
+    #[test]
+    fn test_requested_binaries_appends_unique_extras() {
+        let binaries = requested_binaries(Some(vec![
+            "node".to_string(),
+            " uv ".to_string(),
+            String::new(),
+        ]));
+        assert_eq!(binaries.len(), DEFAULT_SHADOW_BINARIES.len() + 1);
+        assert_eq!(binaries.last().map(String::as_str), Some("uv"));
+    }
+
+    #[test]
+    fn test_mismatched_binaries_compares_winners() {
+        let context = |source, winner: &str| PathShadowContext {
+            source,
+            shell: None,
+            path_entries: Vec::new(),
+            binaries: vec![
+                BinaryShadowInfo {
+                    name: "python".to_string(),
+                    matches: vec![ShadowMatch {
+                        path_index: 0,
+                        dir: String::new(),
+                        binary_path: winner.to_string(),
+                        version: None,
+                        wins: true,
+                    }],
+                    shadowed: false,
+                },
+                BinaryShadowInfo {
+                    name: "go".to_string(),
+                    matches: Vec::new(),
+                    shadowed: false,
+                },
+            ],
+            error: None,
+        };
+        let process = context(PathSource::Process, "/usr/bin/python");
+        let login = context(PathSource::LoginShell, "/home/u/.pyenv/shims/python");
+        assert_eq!(mismatched_binaries(&process, &login), vec!["python"]);
+        assert!(mismatched_binaries(&process, &process).is_empty());
+    }
+}
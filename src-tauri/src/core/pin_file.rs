@@ -0,0 +1,501 @@
+//! Writing local version pins into files shared with other version managers.
+//!
+//! Cognia's providers pin versions in their own files (`.nvmrc`,
+//! `.python-version`, ...). Teams on asdf or mise read `.tool-versions` and
+//! `mise.toml` instead, so a switch can also be written there. Both editors
+//! only touch the tool's own entry: comments, ordering and other tools stay
+//! exactly as they were.
+
+use super::project_env_detect::default_detection_sources;
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::fs;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use toml_edit::{value, Document, Item, Table, Value};
+
+/// File `env_use_local` writes the pin to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PinFileFormat {
+    /// The provider's own version file
+    #[default]
+    Native,
+    /// asdf's `.tool-versions`
+    ToolVersions,
+    /// The `[tools]` table of `mise.toml` / `.mise.toml`
+    Mise,
+}
+
+impl std::fmt::Display for PinFileFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Native => "native",
+            Self::ToolVersions => "tool_versions",
+            Self::Mise => "mise",
+        })
+    }
+}
+
+impl std::str::FromStr for PinFileFormat {
+    type Err = CogniaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "native" => Ok(Self::Native),
+            "tool_versions" | "tool-versions" | ".tool-versions" => Ok(Self::ToolVersions),
+            "mise" => Ok(Self::Mise),
+            _ => Err(CogniaError::Config(
+                "Invalid pin file format. Valid: native, tool_versions, mise".into(),
+            )),
+        }
+    }
+}
+
+impl PinFileFormat {
+    /// Detection source label that reads this file back
+    fn detection_source(self) -> Option<&'static str> {
+        match self {
+            Self::Native => None,
+            Self::ToolVersions => Some(".tool-versions"),
+            Self::Mise => Some("mise.toml"),
+        }
+    }
+}
+
+/// Tool names asdf / mise use for `env_type`, preferred spelling first. These
+/// mirror the names detection accepts.
+fn tool_names(env_type: &str, format: PinFileFormat) -> &'static [&'static str] {
+    match (env_type, format) {
+        ("node", PinFileFormat::ToolVersions) => &["nodejs", "node"],
+        ("node", _) => &["node", "nodejs"],
+        ("go", PinFileFormat::ToolVersions) => &["golang", "go"],
+        ("go", _) => &["go", "golang"],
+        ("r", PinFileFormat::ToolVersions) => &["R"],
+        ("r", _) => &["r", "R"],
+        ("dotnet", _) => &["dotnet", "dotnet-core"],
+        ("dart", _) => &["dart", "flutter"],
+        ("haskell", _) => &["haskell", "ghc"],
+        ("cpp", _) => &["cpp", "c++"],
+        ("python", _) => &["python"],
+        ("rust", _) => &["rust"],
+        ("ruby", _) => &["ruby"],
+        ("java", _) => &["java"],
+        ("kotlin", _) => &["kotlin"],
+        ("scala", _) => &["scala"],
+        ("php", _) => &["php"],
+        ("deno", _) => &["deno"],
+        ("bun", _) => &["bun"],
+        ("zig", _) => &["zig"],
+        ("lua", _) => &["lua"],
+        ("groovy", _) => &["groovy"],
+        ("elixir", _) => &["elixir"],
+        ("erlang", _) => &["erlang"],
+        ("swift", _) => &["swift"],
+        ("julia", _) => &["julia"],
+        ("perl", _) => &["perl"],
+        ("clojure", _) => &["clojure"],
+        ("crystal", _) => &["crystal"],
+        ("nim", _) => &["nim"],
+        ("ocaml", _) => &["ocaml"],
+        ("fortran", _) => &["fortran"],
+        ("c", _) => &["c"],
+        ("typescript", _) => &["typescript"],
+        ("cuda", _) => &["cuda"],
+        _ => &[],
+    }
+}
+
+/// Writes `version` for `env_type` into the `format` pin file in
+/// `project_dir`, creating the file when needed. Returns the file written.
+pub async fn write_pin(
+    project_dir: &Path,
+    env_type: &str,
+    version: &str,
+    format: PinFileFormat,
+) -> CogniaResult<PathBuf> {
+    let names = tool_names(env_type, format);
+    let supported = format
+        .detection_source()
+        .is_some_and(|source| default_detection_sources(env_type).contains(&source));
+    if names.is_empty() || !supported {
+        return Err(CogniaError::Config(format!(
+            "{} pins cannot be written to {}",
+            env_type, format
+        )));
+    }
+
+    let path = match format {
+        PinFileFormat::ToolVersions => project_dir.join(".tool-versions"),
+        // Detection reads `.mise.toml` first, so update that one if present.
+        _ if project_dir.join(".mise.toml").is_file() => project_dir.join(".mise.toml"),
+        _ => project_dir.join("mise.toml"),
+    };
+    let content = if path.is_file() {
+        fs::read_file_string(&path).await?
+    } else {
+        String::new()
+    };
+
+    let updated = match format {
+        PinFileFormat::ToolVersions => set_tool_versions_entry(&content, names, version),
+        _ => set_mise_tool(&content, names, version)?,
+    };
+    if updated != content {
+        fs::write_file_atomic(&path, updated.as_bytes()).await?;
+    }
+    Ok(path)
+}
+
+fn newline_of(content: &str) -> &'static str {
+    if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+fn join_lines(lines: &[String], newline: &str) -> String {
+    let mut out = lines.join(newline);
+    if !out.is_empty() {
+        out.push_str(newline);
+    }
+    out
+}
+
+/// Points the first `.tool-versions` line for any of `names` at `version`,
+/// keeping its indentation and trailing comment, or appends a new line.
+pub fn set_tool_versions_entry(content: &str, names: &[&str], version: &str) -> String {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+
+    let entry = lines.iter().position(|line| {
+        let body = line.split('#').next().unwrap_or("");
+        body.split_whitespace()
+            .next()
+            .is_some_and(|tool| names.contains(&tool))
+    });
+
+    match entry {
+        Some(i) => {
+            let line = &lines[i];
+            let (body, comment) = line.split_at(line.find('#').unwrap_or(line.len()));
+            let indent = &body[..body.len() - body.trim_start().len()];
+            let tool = body.split_whitespace().next().unwrap_or_default();
+            let trimmed = body.trim_end();
+            let gap = if comment.is_empty() {
+                ""
+            } else {
+                &body[trimmed.len()..]
+            };
+            lines[i] = format!("{}{} {}{}{}", indent, tool, version, gap, comment);
+        }
+        None => lines.push(format!("{} {}", names[0], version)),
+    }
+
+    join_lines(&lines, newline_of(content))
+}
+
+/// Keeps the original decoration (spacing and trailing comment) of `value`
+/// while pointing it at `version`.
+fn set_version_value(value: &mut Value, version: &str) {
+    let decor = value.decor().clone();
+    *value = Value::from(version);
+    *value.decor_mut() = decor;
+}
+
+/// Points the `[tools]` entry for any of `names` at `version`, or adds one.
+/// The file is edited through `toml_edit`, so comments, spacing and every
+/// other entry are kept as they were.
+pub fn set_mise_tool(content: &str, names: &[&str], version: &str) -> CogniaResult<String> {
+    // toml_edit writes `\n` line endings, so edit with those and restore CRLF
+    let newline = newline_of(content);
+    let mut doc: Document = content
+        .replace("\r\n", "\n")
+        .parse()
+        .map_err(|e| CogniaError::Parse(format!("Cannot update an invalid mise.toml: {}", e)))?;
+
+    if !doc.contains_key("tools") {
+        // Comments at the end of the file stay ahead of the new table
+        let trailing = doc.trailing().as_str().unwrap_or_default().to_string();
+        let mut tools = Table::new();
+        let gap = if content.trim().is_empty() || trailing.ends_with("\n\n") {
+            ""
+        } else {
+            "\n"
+        };
+        tools.decor_mut().set_prefix(format!("{}{}", trailing, gap));
+        doc.set_trailing("");
+        doc.insert("tools", Item::Table(tools));
+    }
+    let tools = doc["tools"]
+        .as_table_like_mut()
+        .ok_or_else(|| CogniaError::Parse("`tools` in mise.toml is not a table".into()))?;
+    let key = names
+        .iter()
+        .find(|name| tools.contains_key(name))
+        .unwrap_or(&names[0]);
+
+    match tools.get_mut(key) {
+        None => {
+            tools.insert(key, value(version));
+        }
+        // `node = { version = "20", postinstall = "..." }`
+        Some(Item::Value(Value::InlineTable(spec))) if spec.contains_key("version") => {
+            if let Some(current) = spec.get_mut("version") {
+                set_version_value(current, version);
+            }
+        }
+        Some(Item::Value(current)) => set_version_value(current, version),
+        // `[tools.node]` with its own `version = ...`
+        Some(Item::Table(spec)) => match spec.get_mut("version").and_then(Item::as_value_mut) {
+            Some(current) => set_version_value(current, version),
+            None => {
+                return Err(CogniaError::Config(format!(
+                    "[tools.{}] in mise.toml has no version to update; file left unchanged",
+                    key
+                )))
+            }
+        },
+        Some(_) => {
+            return Err(CogniaError::Config(format!(
+                "`{}` in mise.toml is not a version entry; file left unchanged",
+                key
+            )))
+        }
+    }
+
+    let updated = doc.to_string();
+    Ok(if newline == "\n" {
+        updated
+    } else {
+        updated.replace('\n', newline)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_pin_file_format_parse() {
+        assert_eq!(
+            "native".parse::<PinFileFormat>().unwrap(),
+            PinFileFormat::Native
+        );
+        assert_eq!(
+            ".tool-versions".parse::<PinFileFormat>().unwrap(),
+            PinFileFormat::ToolVersions
+        );
+        assert_eq!(
+            "MISE".parse::<PinFileFormat>().unwrap(),
+            PinFileFormat::Mise
+        );
+        assert!("asdf-ish".parse::<PinFileFormat>().is_err());
+        assert_eq!(PinFileFormat::ToolVersions.to_string(), "tool_versions");
+    }
+
+    #[test]
+    fn test_tool_versions_updates_entry_in_place() {
+        let content = "# team toolchain\nnodejs 18.17.0   # LTS\npython 3.12.1\n\n# ruby for docs\nruby 3.3.0\n";
+        let updated = set_tool_versions_entry(content, &["nodejs", "node"], "20.11.0");
+        assert_eq!(
+            updated,
+            "# team toolchain\nnodejs 20.11.0   # LTS\npython 3.12.1\n\n# ruby for docs\nruby 3.3.0\n"
+        );
+    }
+
+    #[test]
+    fn test_tool_versions_keeps_alias_and_drops_fallbacks() {
+        let content = "node 18 16\ngolang 1.22.0\n";
+        let updated = set_tool_versions_entry(content, &["nodejs", "node"], "20.11.0");
+        assert_eq!(updated, "node 20.11.0\ngolang 1.22.0\n");
+    }
+
+    #[test]
+    fn test_tool_versions_appends_missing_tool() {
+        let content = "python 3.12.1\n# nothing else yet";
+        let updated = set_tool_versions_entry(content, &["nodejs", "node"], "20.11.0");
+        assert_eq!(
+            updated,
+            "python 3.12.1\n# nothing else yet\nnodejs 20.11.0\n"
+        );
+        assert_eq!(
+            set_tool_versions_entry("", &["golang", "go"], "1.22.0"),
+            "golang 1.22.0\n"
+        );
+    }
+
+    #[test]
+    fn test_tool_versions_ignores_commented_entries_and_keeps_crlf() {
+        let content = "# nodejs 16.0.0\r\nnodejs 18.0.0\r\n";
+        let updated = set_tool_versions_entry(content, &["nodejs"], "20.0.0");
+        assert_eq!(updated, "# nodejs 16.0.0\r\nnodejs 20.0.0\r\n");
+    }
+
+    #[test]
+    fn test_mise_updates_string_entry_in_place() {
+        let content = r#"# shared with the team
+[env]
+NODE_ENV = "development" # local only
+
+[tools]
+# runtime
+node   =   "18.17.0"  # LTS
+python = "3.12"
+
+[tasks.build]
+run = "npm run build"
+"#;
+        let updated = set_mise_tool(content, &["node", "nodejs"], "20.11.0").unwrap();
+        assert_eq!(
+            updated,
+            content.replace(r#"node   =   "18.17.0""#, r#"node   =   "20.11.0""#)
+        );
+    }
+
+    #[test]
+    fn test_mise_updates_inline_table_version() {
+        let content = "[tools]\nnode = { version = '18', postinstall = 'corepack enable' }\n";
+        let updated = set_mise_tool(content, &["node"], "20.11.0").unwrap();
+        assert_eq!(
+            updated,
+            "[tools]\nnode = { version = \"20.11.0\", postinstall = 'corepack enable' }\n"
+        );
+    }
+
+    #[test]
+    fn test_mise_replaces_multiline_array() {
+        let content = "[tools]\npython = [\n  \"3.12\",\n  \"3.11\",\n] # both\ngo = \"1.22\"\n";
+        let updated = set_mise_tool(content, &["python"], "3.13.0").unwrap();
+        assert_eq!(
+            updated,
+            "[tools]\npython = \"3.13.0\" # both\ngo = \"1.22\"\n"
+        );
+    }
+
+    #[test]
+    fn test_mise_uses_existing_alias() {
+        let content = "[tools]\nnodejs = \"18\"\n";
+        let updated = set_mise_tool(content, &["node", "nodejs"], "20").unwrap();
+        assert_eq!(updated, "[tools]\nnodejs = \"20\"\n");
+    }
+
+    #[test]
+    fn test_mise_inserts_into_existing_tools_table() {
+        let content =
+            "[tools]\npython = \"3.12\" # keep\n\n# tasks below\n[tasks.test]\nrun = \"pytest\"\n";
+        let updated = set_mise_tool(content, &["node", "nodejs"], "20.11.0").unwrap();
+        assert_eq!(
+            updated,
+            "[tools]\npython = \"3.12\" # keep\nnode = \"20.11.0\"\n\n# tasks below\n[tasks.test]\nrun = \"pytest\"\n"
+        );
+    }
+
+    #[test]
+    fn test_mise_appends_tools_table() {
+        let content = "# mise config\n[env]\nFOO = \"bar\"\n";
+        let updated = set_mise_tool(content, &["go", "golang"], "1.22.0").unwrap();
+        assert_eq!(
+            updated,
+            "# mise config\n[env]\nFOO = \"bar\"\n\n[tools]\ngo = \"1.22.0\"\n"
+        );
+        assert_eq!(
+            set_mise_tool("", &["go"], "1.22.0").unwrap(),
+            "[tools]\ngo = \"1.22.0\"\n"
+        );
+    }
+
+    #[test]
+    fn test_mise_updates_root_dotted_key() {
+        let content = "tools.node = \"18\" # pinned\n[env]\nA = \"1\"\n";
+        let updated = set_mise_tool(content, &["node"], "20").unwrap();
+        assert_eq!(updated, "tools.node = \"20\" # pinned\n[env]\nA = \"1\"\n");
+    }
+
+    #[test]
+    fn test_mise_updates_tool_sub_table() {
+        let content = "[tools]\npython = \"3.12\"\n\n[tools.node]\nversion = \"18\" # lts\npostinstall = \"corepack enable\"\n";
+        let updated = set_mise_tool(content, &["node"], "20").unwrap();
+        assert_eq!(updated, content.replace("\"18\"", "\"20\""));
+    }
+
+    #[test]
+    fn test_mise_keeps_crlf_and_trailing_comments() {
+        let content = "[env]\r\nFOO = \"bar\"\r\n\r\n# tools go below\r\n";
+        let updated = set_mise_tool(content, &["node"], "20").unwrap();
+        assert_eq!(
+            updated,
+            "[env]\r\nFOO = \"bar\"\r\n\r\n# tools go below\r\n\r\n[tools]\r\nnode = \"20\"\r\n"
+        );
+    }
+
+    #[test]
+    fn test_mise_updates_inline_tools_table() {
+        let content = "tools = { node = \"18\", python = \"3.12\" }\n";
+        let updated = set_mise_tool(content, &["node"], "20").unwrap();
+        assert_eq!(updated, "tools = { node = \"20\", python = \"3.12\" }\n");
+    }
+
+    #[test]
+    fn test_mise_rejects_invalid_and_unsupported_layouts() {
+        assert!(set_mise_tool("[tools\nnode = ", &["node"], "20").is_err());
+        // A sub-table without `version` cannot also take an entry in `[tools]`.
+        let content = "[tools]\npython = \"3.12\"\n\n[tools.node]\npostinstall = \"x\"\n";
+        assert!(set_mise_tool(content, &["node"], "20").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_write_pin_round_trips_through_detection() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join(".tool-versions"),
+            "# asdf\npython 3.12.1\nnodejs 18.0.0 # old\n",
+        )
+        .unwrap();
+
+        let path = write_pin(dir.path(), "node", "20.11.0", PinFileFormat::ToolVersions)
+            .await
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# asdf\npython 3.12.1\nnodejs 20.11.0 # old\n"
+        );
+
+        let path = write_pin(dir.path(), "node", "20.11.0", PinFileFormat::Mise)
+            .await
+            .unwrap();
+        assert_eq!(path, dir.path().join("mise.toml"));
+
+        let detected = super::super::project_env_detect::detect_env_version(
+            "node",
+            dir.path(),
+            &["mise.toml".to_string()],
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(detected.version, "20.11.0");
+    }
+
+    #[tokio::test]
+    async fn test_write_pin_prefers_dot_mise_toml() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join(".mise.toml"), "[tools]\ngo = \"1.21\"\n").unwrap();
+        let path = write_pin(dir.path(), "go", "1.22.0", PinFileFormat::Mise)
+            .await
+            .unwrap();
+        assert_eq!(path, dir.path().join(".mise.toml"));
+        assert!(!dir.path().join("mise.toml").exists());
+    }
+
+    #[tokio::test]
+    async fn test_write_pin_rejects_native_and_unknown_env() {
+        let dir = TempDir::new().unwrap();
+        assert!(write_pin(dir.path(), "node", "20", PinFileFormat::Native)
+            .await
+            .is_err());
+        assert!(write_pin(dir.path(), "cobol", "1", PinFileFormat::Mise)
+            .await
+            .is_err());
+    }
+}
@@ -69,6 +69,82 @@ pub fn remove_environment(&mut self, env_type: &str) {
     }
 }
 
+/// Format version written by [`ProfileManager::export_portable`]. Imports
+/// reject payloads from newer formats.
+pub const PROFILE_EXPORT_FORMAT_VERSION: u32 = 1;
+
+/// Key fragments treated as secrets when redacting exported env snapshots.
+pub const DEFAULT_SECRET_KEY_PATTERNS: &[&str] = &["TOKEN", "KEY", "SECRET", "PASSWORD"];
+
+const SECRET_PLACEHOLDER_PREFIX: &str = "<<cognia-secret:";
+const SECRET_PLACEHOLDER_SUFFIX: &str = ">>";
+
+/// Machine-portable profile export envelope
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortableProfileExport {
+    pub format_version: u32,
+    pub exported_at: String,
+    /// Env snapshot keys whose values were replaced with placeholders
+    #[serde(default)]
+    pub redacted_keys: Vec<String>,
+    pub profile: EnvironmentProfile,
+}
+
+pub fn secret_placeholder(key: &str) -> String {
+    format!(
+        "{}{}{}",
+        SECRET_PLACEHOLDER_PREFIX, key, SECRET_PLACEHOLDER_SUFFIX
+    )
+}
+
+/// Returns the key named by a secret placeholder value.
+pub fn parse_secret_placeholder(value: &str) -> Option<&str> {
+    value
+        .strip_prefix(SECRET_PLACEHOLDER_PREFIX)?
+        .strip_suffix(SECRET_PLACEHOLDER_SUFFIX)
+        .filter(|key| !key.is_empty())
+}
+
+pub fn is_secret_key(key: &str, patterns: &[String]) -> bool {
+    let key = key.to_uppercase();
+    patterns
+        .iter()
+        .map(|pattern| pattern.trim().to_uppercase())
+        .any(|pattern| !pattern.is_empty() && key.contains(&pattern))
+}
+
+/// Replace secret env snapshot values with placeholders, returning the
+/// redacted keys in sorted order.
+pub fn redact_profile_secrets(
+    profile: &mut EnvironmentProfile,
+    patterns: &[String],
+) -> Vec<String> {
+    let mut redacted = Vec::new();
+    if let Some(snapshot) = profile.env_snapshot.as_mut() {
+        for (key, value) in snapshot.iter_mut() {
+            if !value.is_empty() && is_secret_key(key, patterns) {
+                *value = secret_placeholder(key);
+                redacted.push(key.clone());
+            }
+        }
+    }
+    redacted.sort();
+    redacted
+}
+
+/// Env snapshot keys that still hold secret placeholders, sorted.
+pub fn unresolved_secret_keys(profile: &EnvironmentProfile) -> Vec<String> {
+    let mut keys: Vec<String> = profile
+        .env_snapshot
+        .iter()
+        .flatten()
+        .filter(|(_, value)| parse_secret_placeholder(value).is_some())
+        .map(|(key, _)| key.clone())
+        .collect();
+    keys.sort();
+    keys
+}
+
 /// Result of applying a profile
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProfileApplyResult {
@@ -312,11 +388,47 @@ pub fn export(&self, id: &str) -> CogniaResult<String> {
             .map_err(|e| CogniaError::Parse(format!("Failed to export profile: {}", e)))
     }
 
+    /// Export a profile wrapped in a [`PortableProfileExport`] envelope,
+    /// optionally masking env snapshot values whose keys match `secret_patterns`.
+    pub fn export_portable(
+        &self,
+        id: &str,
+        redact_secrets: bool,
+        secret_patterns: &[String],
+    ) -> CogniaResult<String> {
+        let mut profile = self
+            .get(id)
+            .ok_or_else(|| CogniaError::Provider(format!("Profile with ID {} not found", id)))?;
+
+        let redacted_keys = if redact_secrets {
+            redact_profile_secrets(&mut profile, secret_patterns)
+        } else {
+            Vec::new()
+        };
+
+        let export = PortableProfileExport {
+            format_version: PROFILE_EXPORT_FORMAT_VERSION,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            redacted_keys,
+            profile,
+        };
+        serde_json::to_string_pretty(&export)
+            .map_err(|e| CogniaError::Parse(format!("Failed to export profile: {}", e)))
+    }
+
     /// Import a profile from JSON
     pub async fn import(&mut self, json: &str) -> CogniaResult<EnvironmentProfile> {
-        let mut profile: EnvironmentProfile = serde_json::from_str(json)
+        let profile: EnvironmentProfile = serde_json::from_str(json)
             .map_err(|e| CogniaError::Parse(format!("Failed to parse profile: {}", e)))?;
 
+        self.import_profile(profile).await
+    }
+
+    /// Import an already-validated profile under a fresh ID
+    pub async fn import_profile(
+        &mut self,
+        mut profile: EnvironmentProfile,
+    ) -> CogniaResult<EnvironmentProfile> {
         // Generate new ID to avoid conflicts
         profile.id = Uuid::new_v4().to_string();
         profile.created_at = chrono::Utc::now().to_rfc3339();
@@ -325,6 +437,32 @@ pub async fn import(&mut self, json: &str) -> CogniaResult<EnvironmentProfile> {
         self.create(profile).await
     }
 
+    /// Current global version of every environment provider that reports one,
+    /// one entry per environment type
+    pub async fn capture_current_environments(&self) -> Vec<ProfileEnvironment> {
+        let mut captured = EnvironmentProfile::new("");
+        let registry = self.registry.read().await;
+        let provider_ids: Vec<String> = registry
+            .list_environment_providers()
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        for provider_id in provider_ids {
+            if let Some(provider) = registry.get_environment_provider(&provider_id) {
+                if let Ok(Some(version)) = provider.get_current_version().await {
+                    let env_type = self.provider_to_env_type(&provider_id);
+                    captured.add_environment(ProfileEnvironment {
+                        env_type,
+                        version,
+                        provider_id: Some(provider_id),
+                    });
+                }
+            }
+        }
+        captured.environments
+    }
+
     /// Create a profile from current environment state
     pub async fn create_from_current(
         &mut self,
@@ -333,29 +471,9 @@ pub async fn create_from_current(
         include_env_snapshot: bool,
     ) -> CogniaResult<EnvironmentProfile> {
         let mut profile = EnvironmentProfile::new(name);
-
-        // Collect environment info while holding the read lock
-        {
-            let registry = self.registry.read().await;
-            let provider_ids: Vec<String> = registry
-                .list_environment_providers()
-                .iter()
-                .map(|s| s.to_string())
-                .collect();
-
-            for provider_id in provider_ids {
-                if let Some(provider) = registry.get_environment_provider(&provider_id) {
-                    if let Ok(Some(version)) = provider.get_current_version().await {
-                        let env_type = self.provider_to_env_type(&provider_id);
-                        profile.add_environment(ProfileEnvironment {
-                            env_type,
-                            version,
-                            provider_id: Some(provider_id),
-                        });
-                    }
-                }
-            }
-        } // registry lock is dropped here
+        for env in self.capture_current_environments().await {
+            profile.add_environment(env);
+        }
 
         profile.env_snapshot = self
             .capture_env_snapshot_if_requested(include_env_snapshot)
@@ -418,17 +536,41 @@ async fn apply_env_snapshot_if_present(
         let current_vars = env::list_persistent_vars(EnvVarScope::User).await?;
         let current_map: HashMap<String, String> = current_vars.into_iter().collect();
 
-        let removed_keys: Vec<String> = current_map
+        // PATH is edited entry by entry; it is never dropped wholesale.
+        let mut transaction = env::EnvVarTransaction::new(EnvVarScope::User);
+        for key in current_map
             .keys()
-            .filter(|key| !snapshot.contains_key(*key))
-            .cloned()
-            .collect();
-        for key in removed_keys {
-            env::remove_persistent_var(&key, EnvVarScope::User).await?;
+            .filter(|key| !env::is_path_key(key) && !snapshot.contains_key(*key))
+        {
+            transaction.remove(key.as_str());
         }
-
         for (key, value) in snapshot {
-            env::set_persistent_var(key, value, EnvVarScope::User).await?;
+            if !env::is_path_key(key) {
+                transaction.set(key.as_str(), value.as_str());
+                continue;
+            }
+            // The profile's own `$PATH` reference is not an entry to restore
+            let entries = |list: &str| -> Vec<String> {
+                env::split_path_list(list)
+                    .into_iter()
+                    .filter(|entry| !env::is_inherited_path_reference(entry))
+                    .collect()
+            };
+            let desired = entries(value);
+            let current = current_map
+                .iter()
+                .find(|(current_key, _)| env::is_path_key(current_key))
+                .map(|(_, current)| entries(current))
+                .unwrap_or_default();
+            for entry in current.iter().filter(|entry| !desired.contains(entry)) {
+                transaction.path_remove(entry.as_str());
+            }
+            for entry in desired {
+                transaction.path_add(entry, false);
+            }
+        }
+        if !transaction.is_empty() {
+            transaction.apply().await?;
         }
 
         Ok(())
@@ -523,7 +665,7 @@ async fn apply_wsl_snapshot_if_present(
     }
 
     /// Map environment type to default provider ID
-    fn env_type_to_default_provider(&self, env_type: &str) -> String {
+    pub fn env_type_to_default_provider(&self, env_type: &str) -> String {
         match env_type.to_lowercase().as_str() {
             "node" => "fnm".to_string(),
             "deno" => "deno".to_string(),
@@ -949,6 +1091,43 @@ async fn test_profile_manager_export() {
         assert_eq!(parsed.environments.len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_profile_manager_export_portable_redacts_secrets() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = Arc::new(RwLock::new(ProviderRegistry::new()));
+        let mut manager = ProfileManager::new(dir.path().to_path_buf(), registry);
+
+        let mut profile = EnvironmentProfile::new("Portable");
+        profile.env_snapshot = Some(HashMap::from([
+            ("GITHUB_TOKEN".to_string(), "ghp_abc".to_string()),
+            ("db_password".to_string(), "hunter2".to_string()),
+            ("EDITOR".to_string(), "vim".to_string()),
+        ]));
+        let id = manager.create(profile).await.unwrap().id;
+        let patterns: Vec<String> = DEFAULT_SECRET_KEY_PATTERNS
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+
+        let json = manager.export_portable(&id, true, &patterns).unwrap();
+        let export: PortableProfileExport = serde_json::from_str(&json).unwrap();
+        assert_eq!(export.format_version, PROFILE_EXPORT_FORMAT_VERSION);
+        assert_eq!(export.redacted_keys, vec!["GITHUB_TOKEN", "db_password"]);
+        let snapshot = export.profile.env_snapshot.as_ref().unwrap();
+        assert_eq!(
+            parse_secret_placeholder(&snapshot["GITHUB_TOKEN"]),
+            Some("GITHUB_TOKEN")
+        );
+        assert_eq!(snapshot["EDITOR"], "vim");
+        assert_eq!(
+            unresolved_secret_keys(&export.profile),
+            vec!["GITHUB_TOKEN", "db_password"]
+        );
+
+        let raw = manager.export_portable(&id, false, &patterns).unwrap();
+        assert!(raw.contains("ghp_abc"));
+    }
+
     #[tokio::test]
     async fn test_profile_manager_export_nonexistent() {
         let registry = ProviderRegistry::new();
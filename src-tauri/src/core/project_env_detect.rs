@@ -13,6 +13,7 @@
 const CPP_VCPKG_CONFIGURATION_SOURCE: &str = "vcpkg-configuration.json";
 const CPP_CONANFILE_TXT_SOURCE: &str = "conanfile.txt";
 const CPP_CONANFILE_PY_SOURCE: &str = "conanfile.py";
+const CUDA_ENVIRONMENT_YML_SOURCE: &str = "environment.yml (cudatoolkit)";
 
 /// Default detection sources for a logical environment type.
 ///
@@ -30,15 +31,22 @@ pub fn default_detection_sources(env_type: &str) -> &'static [&'static str] {
         ],
         "python" => &[
             ".python-version",
+            "pyproject.toml (tool.uv.python)",
             "pyproject.toml (project.requires-python)",
             "pyproject.toml (tool.poetry.dependencies.python)",
             "uv.toml (requires-python)",
+            "pixi.toml (dependencies.python)",
+            "pixi.lock (python)",
+            "conda-lock.yml (python)",
             "Pipfile (requires.python_version)",
             "runtime.txt",
             ".tool-versions",
             "mise.toml",
         ],
+        // A go.work workspace governs the toolchain for every module it uses.
         "go" => &[
+            "go.work (toolchain)",
+            "go.work (go)",
             "go.mod (toolchain)",
             "go.mod (go)",
             ".go-version",
@@ -189,6 +197,12 @@ pub fn default_detection_sources(env_type: &str) -> &'static [&'static str] {
         ],
         "ocaml" => &[".ocaml-version", ".tool-versions", "mise.toml"],
         "fortran" => &[".tool-versions", "mise.toml"],
+        "cuda" => &[
+            ".cuda-version",
+            CUDA_ENVIRONMENT_YML_SOURCE,
+            ".tool-versions",
+            "mise.toml",
+        ],
         _ => &[],
     }
 }
@@ -236,13 +250,16 @@ pub fn classify_detection_source(env_type: &str, source: &str) -> String {
         || source.contains("build.zig.zon")
         || source.contains("Cargo.toml")
         || source.contains("go.mod")
+        || source.contains("go.work")
         || source.contains("Pipfile")
         || source.contains("pyproject.toml")
+        || source.contains("pixi.toml")
         || source.contains("pubspec.yaml")
         || source.contains("Gemfile")
         || source.contains("conanfile")
         || source == CPP_VCPKG_MANIFEST_SOURCE
         || source == CPP_VCPKG_CONFIGURATION_SOURCE
+        || source == CUDA_ENVIRONMENT_YML_SOURCE
         || source.contains("manifest")
     {
         "manifest".to_string()
@@ -274,6 +291,9 @@ pub async fn detect_env_version(
                     source: version.source,
                     source_path: Some(version.path),
                     source_type: classify_detection_source(env_type, source),
+                    resolved_via: None,
+                    resolved_version: None,
+                    suggested_version: None,
                 }));
             }
         }
@@ -286,6 +306,40 @@ pub async fn detect_env_version(
     Ok(None)
 }
 
+/// Every pin visible from `start_path`, in the order [`detect_env_version`] would
+/// consider them: nearest directory first, then source priority. The first
+/// entry is the one that wins; the rest are shadowed.
+pub async fn detect_env_version_candidates(
+    env_type: &str,
+    start_path: &Path,
+    sources_in_priority: &[String],
+) -> CogniaResult<Vec<DetectedEnvironment>> {
+    let mut current = normalize_start_dir(start_path);
+    let mut candidates = Vec::new();
+    loop {
+        for source in sources_in_priority {
+            if let Some(version) = detect_from_source(env_type, &current, source).await? {
+                candidates.push(DetectedEnvironment {
+                    env_type: env_type.to_string(),
+                    version: version.value,
+                    source: version.source,
+                    source_path: Some(version.path),
+                    source_type: classify_detection_source(env_type, source),
+                    resolved_via: None,
+                    resolved_version: None,
+                    suggested_version: None,
+                });
+            }
+        }
+
+        if !current.pop() {
+            break;
+        }
+    }
+
+    Ok(candidates)
+}
+
 #[derive(Debug, Clone)]
 struct DetectedValue {
     value: String,
@@ -341,6 +395,7 @@ async fn detect_from_source(
         "c" => detect_c(dir, source).await,
         "cpp" => detect_cpp(dir, source).await,
         "typescript" => detect_typescript(dir, source).await,
+        "cuda" => detect_cuda(dir, source).await,
         _ => Ok(None),
     }
 }
@@ -380,10 +435,20 @@ async fn detect_python(dir: &Path, source: &str) -> CogniaResult<Option<Detected
         "pyproject.toml (tool.poetry.dependencies.python)" => {
             read_pyproject_poetry_python(dir.join("pyproject.toml")).await
         }
+        "pyproject.toml (tool.uv.python)" => {
+            read_pyproject_uv_python(dir.join("pyproject.toml")).await
+        }
         "Pipfile" | "Pipfile (requires.python_version)" => {
             read_pipfile_python(dir.join("Pipfile")).await
         }
         "uv.toml (requires-python)" => read_uv_toml_requires_python(dir.join("uv.toml")).await,
+        "pixi.toml" | "pixi.toml (dependencies.python)" => {
+            read_pixi_toml_python(dir.join("pixi.toml")).await
+        }
+        "pixi.lock" | "pixi.lock (python)" => read_pixi_lock_python(dir.join("pixi.lock")).await,
+        "conda-lock.yml" | "conda-lock.yml (python)" => {
+            read_conda_lock_python(dir.join("conda-lock.yml")).await
+        }
         "runtime.txt" => read_runtime_txt_python(dir.join("runtime.txt")).await,
         ".tool-versions" => {
             read_tool_versions(dir.join(".tool-versions"), &["python"], ".tool-versions").await
@@ -398,8 +463,10 @@ async fn detect_go(dir: &Path, source: &str) -> CogniaResult<Option<DetectedValu
         ".go-version" => read_version_file(dir.join(".go-version"), ".go-version").await,
         // Legacy label: parse toolchain first, then go directive.
         "go.mod" => read_go_mod(dir.join("go.mod")).await,
-        "go.mod (toolchain)" => read_go_mod_toolchain(dir.join("go.mod")).await,
-        "go.mod (go)" => read_go_mod_go(dir.join("go.mod")).await,
+        "go.work (toolchain)" => read_go_toolchain_directive(dir.join("go.work"), source).await,
+        "go.work (go)" => read_go_version_directive(dir.join("go.work"), source).await,
+        "go.mod (toolchain)" => read_go_toolchain_directive(dir.join("go.mod"), source).await,
+        "go.mod (go)" => read_go_version_directive(dir.join("go.mod"), source).await,
         ".tool-versions" => {
             read_tool_versions(
                 dir.join(".tool-versions"),
@@ -1219,7 +1286,11 @@ async fn read_go_mod(path: PathBuf) -> CogniaResult<Option<DetectedValue>> {
     Ok(None)
 }
 
-async fn read_go_mod_toolchain(path: PathBuf) -> CogniaResult<Option<DetectedValue>> {
+/// Read the `toolchain` directive from a go.mod or go.work file
+async fn read_go_toolchain_directive(
+    path: PathBuf,
+    source: &str,
+) -> CogniaResult<Option<DetectedValue>> {
     if !path.is_file() {
         return Ok(None);
     }
@@ -1233,7 +1304,7 @@ async fn read_go_mod_toolchain(path: PathBuf) -> CogniaResult<Option<DetectedVal
         if let Some(value) = normalize_go_toolchain_name(&toolchain) {
             return Ok(Some(DetectedValue {
                 value,
-                source: "go.mod (toolchain)".to_string(),
+                source: source.to_string(),
                 path,
             }));
         }
@@ -1274,7 +1345,11 @@ fn normalize_go_toolchain_name(raw: &str) -> Option<String> {
     }
 }
 
-async fn read_go_mod_go(path: PathBuf) -> CogniaResult<Option<DetectedValue>> {
+/// Read the `go` directive from a go.mod or go.work file
+async fn read_go_version_directive(
+    path: PathBuf,
+    source: &str,
+) -> CogniaResult<Option<DetectedValue>> {
     if !path.is_file() {
         return Ok(None);
     }
@@ -1289,7 +1364,7 @@ async fn read_go_mod_go(path: PathBuf) -> CogniaResult<Option<DetectedValue>> {
         if !go.is_empty() {
             return Ok(Some(DetectedValue {
                 value: go.to_string(),
-                source: "go.mod (go)".to_string(),
+                source: source.to_string(),
                 path,
             }));
         }
@@ -1442,6 +1517,41 @@ fn first_nonempty_noncomment_line(content: &str, comment_prefix: char) -> Option
     None
 }
 
+/// Interpreter pin uv reads from `[tool.uv] python = "..."`
+async fn read_pyproject_uv_python(path: PathBuf) -> CogniaResult<Option<DetectedValue>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let content = match crate::platform::fs::read_file_string(&path).await {
+        Ok(s) => s,
+        Err(_) => return Ok(None),
+    };
+
+    let doc: toml::Value = match toml::from_str(&content) {
+        Ok(v) => v,
+        Err(_) => return Ok(None),
+    };
+
+    if let Some(py) = doc
+        .get("tool")
+        .and_then(|v| v.get("uv"))
+        .and_then(|v| v.get("python"))
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+    {
+        if !py.is_empty() {
+            return Ok(Some(DetectedValue {
+                value: py.to_string(),
+                source: "pyproject.toml (tool.uv.python)".to_string(),
+                path,
+            }));
+        }
+    }
+
+    Ok(None)
+}
+
 // ── uv.toml parser for Python ──
 
 async fn read_uv_toml_requires_python(path: PathBuf) -> CogniaResult<Option<DetectedValue>> {
@@ -1476,6 +1586,131 @@ async fn read_uv_toml_requires_python(path: PathBuf) -> CogniaResult<Option<Dete
     Ok(None)
 }
 
+// ── pixi parsers for Python ──
+
+/// Read the python spec from `pixi.toml` `[dependencies]`, falling back to
+/// the first `[feature.<name>.dependencies]` table that pins python.
+async fn read_pixi_toml_python(path: PathBuf) -> CogniaResult<Option<DetectedValue>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let content = match crate::platform::fs::read_file_string(&path).await {
+        Ok(s) => s,
+        Err(_) => return Ok(None),
+    };
+
+    let doc: toml::Value = match toml::from_str(&content) {
+        Ok(v) => v,
+        Err(_) => return Ok(None),
+    };
+
+    let from_table = |deps: Option<&toml::Value>| -> Option<String> {
+        let spec = match deps?.get("python")? {
+            toml::Value::String(s) => s.trim().to_string(),
+            // `python = { version = "3.12.*", channel = "conda-forge" }`
+            toml::Value::Table(t) => t.get("version")?.as_str()?.trim().to_string(),
+            _ => return None,
+        };
+        (!spec.is_empty() && spec != "*").then_some(spec)
+    };
+
+    let spec = from_table(doc.get("dependencies")).or_else(|| {
+        doc.get("feature")
+            .and_then(|v| v.as_table())
+            .and_then(|features| {
+                features
+                    .values()
+                    .find_map(|feature| from_table(feature.get("dependencies")))
+            })
+    });
+
+    Ok(spec.map(|value| DetectedValue {
+        value,
+        source: "pixi.toml (dependencies.python)".to_string(),
+        path,
+    }))
+}
+
+/// Split a conda package archive URL into `(name, version)`.
+/// Example: `.../linux-64/python-3.12.3-hab00c5b_0_cpython.conda` -> `("python", "3.12.3")`.
+fn parse_conda_archive_url(url: &str) -> Option<(String, String)> {
+    let file_name = url.trim().rsplit('/').next()?;
+    let stem = file_name
+        .strip_suffix(".conda")
+        .or_else(|| file_name.strip_suffix(".tar.bz2"))?;
+    let mut parts = stem.rsplitn(3, '-');
+    let _build = parts.next()?;
+    let version = parts.next()?;
+    let name = parts.next()?;
+    if name.is_empty() || version.is_empty() {
+        return None;
+    }
+    Some((name.to_string(), version.to_string()))
+}
+
+/// Read the locked python version from `pixi.lock`.
+///
+/// Works for both the v6 layout (`- conda: <url>`) and older layouts that
+/// carry `url:` next to `name:`/`version:` keys, by looking at conda archive URLs.
+async fn read_pixi_lock_python(path: PathBuf) -> CogniaResult<Option<DetectedValue>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let content = match crate::platform::fs::read_file_string(&path).await {
+        Ok(s) => s,
+        Err(_) => return Ok(None),
+    };
+
+    for line in content.lines() {
+        let trimmed = line.trim().trim_start_matches("- ");
+        let url = match trimmed
+            .strip_prefix("conda:")
+            .or_else(|| trimmed.strip_prefix("url:"))
+        {
+            Some(url) => url.trim(),
+            None => continue,
+        };
+        if let Some((name, version)) = parse_conda_archive_url(url) {
+            if name == "python" {
+                return Ok(Some(DetectedValue {
+                    value: version,
+                    source: "pixi.lock (python)".to_string(),
+                    path,
+                }));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Read the locked python version from a unified `conda-lock.yml`, preferring
+/// the running platform.
+async fn read_conda_lock_python(path: PathBuf) -> CogniaResult<Option<DetectedValue>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let content = match crate::platform::fs::read_file_string(&path).await {
+        Ok(s) => s,
+        Err(_) => return Ok(None),
+    };
+    let Ok(lock) = crate::core::conda_env_spec::parse_conda_lock(&content) else {
+        return Ok(None);
+    };
+
+    let platform = crate::core::conda_env_spec::current_conda_subdir();
+    Ok(lock
+        .conda_version("python", &platform)
+        .map(|version| DetectedValue {
+            value: version.to_string(),
+            source: "conda-lock.yml (python)".to_string(),
+            path,
+        }))
+}
+
 // ── mise.toml parser ──
 
 /// Read version from mise.toml or .mise.toml `[tools]` section.
@@ -1714,6 +1949,18 @@ async fn detect_fortran(dir: &Path, source: &str) -> CogniaResult<Option<Detecte
     }
 }
 
+async fn detect_cuda(dir: &Path, source: &str) -> CogniaResult<Option<DetectedValue>> {
+    match source {
+        ".cuda-version" => read_version_file(dir.join(".cuda-version"), ".cuda-version").await,
+        CUDA_ENVIRONMENT_YML_SOURCE => read_environment_yml_cuda(dir.join("environment.yml")).await,
+        ".tool-versions" => {
+            read_tool_versions(dir.join(".tool-versions"), &["cuda"], ".tool-versions").await
+        }
+        "mise.toml" => read_mise_toml(dir, &["cuda"], source).await,
+        _ => Ok(None),
+    }
+}
+
 // ── New manifest parsers ──
 
 async fn read_mix_exs_elixir_version(path: PathBuf) -> CogniaResult<Option<DetectedValue>> {
@@ -1946,6 +2193,67 @@ async fn read_stack_yaml_resolver(path: PathBuf) -> CogniaResult<Option<Detected
     }))
 }
 
+/// Version part of a conda match spec such as `cudatoolkit=11.8`,
+/// `nvidia::cuda-toolkit==12.1.0=0` or `cudatoolkit >=11.2`, if the package
+/// is one of `names`. Build strings and trailing `.*` are dropped.
+fn parse_conda_spec_version(spec: &str, names: &[&str]) -> Option<String> {
+    let spec = spec.trim();
+    let spec = spec.rsplit_once("::").map_or(spec, |(_, rest)| rest);
+    let name_end = spec
+        .find(|c: char| matches!(c, '=' | '<' | '>' | '!' | '~' | ' '))
+        .unwrap_or(spec.len());
+    let (name, rest) = spec.split_at(name_end);
+    if !names.contains(&name) {
+        return None;
+    }
+
+    let rest = rest.trim();
+    let version = if let Some(exact) = rest.strip_prefix("==") {
+        exact.split('=').next().unwrap_or("")
+    } else if let Some(fuzzy) = rest.strip_prefix('=') {
+        fuzzy.split('=').next().unwrap_or("")
+    } else if rest.starts_with(['<', '>', '!', '~']) {
+        rest
+    } else {
+        rest.split_whitespace().next().unwrap_or("")
+    };
+    let version = version.trim().trim_end_matches(".*");
+    (!version.is_empty()).then(|| version.to_string())
+}
+
+/// environment.yml `dependencies: [cudatoolkit=11.8]` (conda)
+async fn read_environment_yml_cuda(path: PathBuf) -> CogniaResult<Option<DetectedValue>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let content = match crate::platform::fs::read_file_string(&path).await {
+        Ok(s) => s,
+        Err(_) => return Ok(None),
+    };
+
+    let doc: serde_json::Value = match serde_yaml::from_str(&content) {
+        Ok(v) => v,
+        Err(_) => return Ok(None),
+    };
+
+    let version = doc
+        .get("dependencies")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|dep| dep.as_str())
+        .find_map(|dep| {
+            parse_conda_spec_version(dep, &["cudatoolkit", "cuda-toolkit", "cuda-version"])
+        });
+
+    Ok(version.map(|value| DetectedValue {
+        value,
+        source: CUDA_ENVIRONMENT_YML_SOURCE.to_string(),
+        path,
+    }))
+}
+
 /// cabal.project `with-compiler: ghc-9.6.3` (Haskell Cabal)
 async fn read_cabal_project_with(path: PathBuf) -> CogniaResult<Option<DetectedValue>> {
     if !path.is_file() {
@@ -2527,6 +2835,41 @@ mod tests {
     use super::*;
     use tempfile::tempdir;
 
+    #[tokio::test]
+    async fn lists_all_candidates_nearest_first() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        let child = root.join("child");
+        tokio::fs::create_dir_all(&child).await.unwrap();
+
+        crate::platform::fs::write_file_string(root.join(".nvmrc"), "20.10.0")
+            .await
+            .unwrap();
+        crate::platform::fs::write_file_string(child.join(".node-version"), "18.19.0")
+            .await
+            .unwrap();
+        crate::platform::fs::write_file_string(child.join(".nvmrc"), "16.20.0")
+            .await
+            .unwrap();
+
+        let sources = vec![".nvmrc".to_string(), ".node-version".to_string()];
+        let candidates = detect_env_version_candidates("node", &child, &sources)
+            .await
+            .unwrap();
+        let versions: Vec<&str> = candidates
+            .iter()
+            .take(3)
+            .map(|c| c.version.as_str())
+            .collect();
+        assert_eq!(versions, vec!["16.20.0", "18.19.0", "20.10.0"]);
+
+        let winner = detect_env_version("node", &child, &sources)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(winner.version, candidates[0].version);
+    }
+
     #[tokio::test]
     async fn detects_node_from_local_file_before_parent() {
         let dir = tempdir().unwrap();
@@ -2556,6 +2899,89 @@ async fn detects_node_from_local_file_before_parent() {
         assert_eq!(detected.source, ".node-version");
     }
 
+    #[tokio::test]
+    async fn detects_python_from_pyproject_uv_pin_before_requires_python() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+
+        crate::platform::fs::write_file_string(
+            root.join("pyproject.toml"),
+            "[project]\nname = \"demo\"\nrequires-python = \">=3.10\"\n\n[tool.uv]\npython = \"3.12\"\n",
+        )
+        .await
+        .unwrap();
+
+        let sources: Vec<String> = default_detection_sources("python")
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let detected = detect_env_version("python", root, &sources)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(detected.version, "3.12");
+        assert_eq!(detected.source, "pyproject.toml (tool.uv.python)");
+    }
+
+    #[tokio::test]
+    async fn detects_python_from_pixi_toml_and_lock() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+
+        crate::platform::fs::write_file_string(
+            root.join("pixi.toml"),
+            "[project]\nname = \"demo\"\n\n[feature.py.dependencies]\npython = { version = \"3.11.*\" }\n",
+        )
+        .await
+        .unwrap();
+        crate::platform::fs::write_file_string(
+            root.join("pixi.lock"),
+            "version: 6\npackages:\n- conda: https://conda.anaconda.org/conda-forge/linux-64/numpy-2.0.0-py311h_0.conda\n- conda: https://conda.anaconda.org/conda-forge/linux-64/python-3.11.9-hb806964_0_cpython.conda\n  sha256: abc\n",
+        )
+        .await
+        .unwrap();
+
+        let sources = vec!["pixi.toml (dependencies.python)".to_string()];
+        let detected = detect_env_version("python", root, &sources)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(detected.version, "3.11.*");
+        assert_eq!(detected.source_type, "manifest");
+
+        let sources = vec!["pixi.lock (python)".to_string()];
+        let detected = detect_env_version("python", root, &sources)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(detected.version, "3.11.9");
+        assert_eq!(detected.source, "pixi.lock (python)");
+    }
+
+    #[tokio::test]
+    async fn detects_python_from_conda_lock() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        let platform = crate::core::conda_env_spec::current_conda_subdir();
+
+        crate::platform::fs::write_file_string(
+            root.join("conda-lock.yml"),
+            &format!(
+                "version: 1\nmetadata:\n  platforms: [other-64, {platform}]\npackage:\n  - name: python\n    version: 3.10.14\n    manager: conda\n    platform: other-64\n  - name: python\n    version: 3.12.3\n    manager: conda\n    platform: {platform}\n"
+            ),
+        )
+        .await
+        .unwrap();
+
+        let sources = vec!["conda-lock.yml (python)".to_string()];
+        let detected = detect_env_version("python", root, &sources)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(detected.version, "3.12.3");
+        assert_eq!(detected.source, "conda-lock.yml (python)");
+    }
+
     #[tokio::test]
     async fn detects_node_from_engines_when_enabled() {
         let dir = tempdir().unwrap();
@@ -2629,6 +3055,76 @@ async fn go_mod_toolchain_default_falls_back_to_go() {
         assert_eq!(detected.source, "go.mod (go)");
     }
 
+    #[tokio::test]
+    async fn go_work_detected_in_workspace_root_without_go_mod() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        let module = root.join("services").join("api");
+        std::fs::create_dir_all(&module).unwrap();
+
+        crate::platform::fs::write_file_string(
+            root.join("go.work"),
+            r#"
+go 1.22.0
+toolchain go1.22.4 // workspace pin
+
+use (
+    ./services/api
+)
+"#,
+        )
+        .await
+        .unwrap();
+
+        let sources: Vec<String> = default_detection_sources("go")
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(sources[0], "go.work (toolchain)");
+        assert_eq!(sources[1], "go.work (go)");
+
+        let detected = detect_env_version("go", root, &sources)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(detected.version, "1.22.4");
+        assert_eq!(detected.source, "go.work (toolchain)");
+        assert_eq!(detected.source_type, "manifest");
+
+        // Module directories without their own pin inherit the workspace version.
+        let detected = detect_env_version("go", &module, &sources)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(detected.version, "1.22.4");
+        assert_eq!(detected.source_path, Some(root.join("go.work")));
+    }
+
+    #[tokio::test]
+    async fn go_work_local_toolchain_falls_back_to_go_directive() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+
+        crate::platform::fs::write_file_string(
+            root.join("go.work"),
+            "go 1.21.5\ntoolchain local\n\nuse ./a\n",
+        )
+        .await
+        .unwrap();
+
+        let sources = vec![
+            "go.work (toolchain)".to_string(),
+            "go.work (go)".to_string(),
+        ];
+        let detected = detect_env_version("go", root, &sources)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(detected.version, "1.21.5");
+        assert_eq!(detected.source, "go.work (go)");
+    }
+
     #[tokio::test]
     async fn rust_toolchain_file_wins_over_rust_toolchain_toml() {
         let dir = tempdir().unwrap();
@@ -4482,6 +4978,7 @@ async fn default_detection_sources_returns_non_empty_for_known_types() {
             "nim",
             "ocaml",
             "fortran",
+            "cuda",
         ] {
             let sources = default_detection_sources(env_type);
             assert!(
@@ -4865,4 +5362,76 @@ async fn cpp_provider_marker_takes_priority_over_tool_versions() {
         assert_eq!(detected.source, "vcpkg.json");
         assert_eq!(detected.source_type, "manifest");
     }
+
+    // ── CUDA tests ──
+
+    #[test]
+    fn conda_spec_version_handles_operators_and_channels() {
+        let names = &["cudatoolkit", "cuda-version"];
+        assert_eq!(
+            parse_conda_spec_version("cudatoolkit=11.8", names),
+            Some("11.8".into())
+        );
+        assert_eq!(
+            parse_conda_spec_version("conda-forge::cudatoolkit==11.3.1=h2bc3f7f_2", names),
+            Some("11.3.1".into())
+        );
+        assert_eq!(
+            parse_conda_spec_version("cudatoolkit >=11.2", names),
+            Some(">=11.2".into())
+        );
+        assert_eq!(
+            parse_conda_spec_version("cuda-version 12.1.*", names),
+            Some("12.1".into())
+        );
+        assert_eq!(parse_conda_spec_version("cudatoolkit", names), None);
+        assert_eq!(parse_conda_spec_version("cudnn=8.9", names), None);
+    }
+
+    #[tokio::test]
+    async fn cuda_detects_environment_yml_cudatoolkit() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+
+        crate::platform::fs::write_file_string(
+            root.join("environment.yml"),
+            "name: train\nchannels:\n  - nvidia\ndependencies:\n  - python=3.10\n  - cudatoolkit=11.8\n  - pip:\n    - torch\n",
+        )
+        .await
+        .unwrap();
+
+        let sources = vec![CUDA_ENVIRONMENT_YML_SOURCE.to_string()];
+        let detected = detect_env_version("cuda", root, &sources)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(detected.env_type, "cuda");
+        assert_eq!(detected.version, "11.8");
+        assert_eq!(detected.source_type, "manifest");
+    }
+
+    #[tokio::test]
+    async fn cuda_version_file_takes_priority_over_environment_yml() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+
+        crate::platform::fs::write_file_string(root.join(".cuda-version"), "12.4\n")
+            .await
+            .unwrap();
+        crate::platform::fs::write_file_string(
+            root.join("environment.yml"),
+            "dependencies:\n  - cudatoolkit=11.8\n",
+        )
+        .await
+        .unwrap();
+
+        let sources = default_enabled_detection_sources("cuda");
+        let detected = detect_env_version("cuda", root, &sources)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(detected.version, "12.4");
+        assert_eq!(detected.source, ".cuda-version");
+        assert_eq!(detected.source_type, "local");
+    }
 }
@@ -0,0 +1,607 @@
+//! Runnable project tasks.
+//!
+//! Tasks are collected from `package.json` scripts, Makefile targets,
+//! justfile recipes and Taskfile tasks. Each task id is prefixed with its
+//! source (`npm:build`, `make:build`, ...) so equally named tasks from
+//! different files do not collide.
+
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::fs;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+const MAKEFILE_NAMES: &[&str] = &["GNUmakefile", "makefile", "Makefile"];
+const JUSTFILE_NAMES: &[&str] = &["justfile", ".justfile", "Justfile"];
+const TASKFILE_NAMES: &[&str] = &[
+    "Taskfile.yml",
+    "Taskfile.yaml",
+    "taskfile.yml",
+    "taskfile.yaml",
+];
+/// Nested `include` directives followed before giving up
+const MAX_MAKE_INCLUDE_DEPTH: usize = 8;
+
+static MAKE_TARGET_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^([^:=#\t][^:=#]*?)\s*::?(?:[^=]|$)").expect("valid regex"));
+static MAKE_INCLUDE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?:-|s)?include\s+(.+)$").expect("valid regex"));
+static JUST_RECIPE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^@?([A-Za-z_][A-Za-z0-9_-]*)(?:\s+[^:]*)?:(?:[^=]|$)").expect("valid regex")
+});
+
+/// Where a task was declared
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectTaskSource {
+    PackageJson,
+    Makefile,
+    Justfile,
+    Taskfile,
+}
+
+impl ProjectTaskSource {
+    /// Prefix used to namespace task ids
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            Self::PackageJson => "npm",
+            Self::Makefile => "make",
+            Self::Justfile => "just",
+            Self::Taskfile => "task",
+        }
+    }
+}
+
+/// A task that can be run from the project root
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTask {
+    /// `<source prefix>:<name>`, unique within a project
+    pub id: String,
+    pub name: String,
+    pub source: ProjectTaskSource,
+    pub source_file: String,
+    pub description: Option<String>,
+    pub program: String,
+    pub args: Vec<String>,
+    /// Full command line, for display
+    pub command: String,
+    /// Environment that should be activated when running the task. Tasks
+    /// without one run with every environment the project pins.
+    pub env_type: Option<String>,
+}
+
+impl ProjectTask {
+    fn new(
+        source: ProjectTaskSource,
+        name: &str,
+        source_file: &Path,
+        program: &str,
+        args: Vec<String>,
+    ) -> Self {
+        let command = std::iter::once(program.to_string())
+            .chain(args.iter().cloned())
+            .collect::<Vec<_>>()
+            .join(" ");
+        Self {
+            id: format!("{}:{}", source.prefix(), name),
+            name: name.to_string(),
+            source,
+            source_file: source_file.display().to_string(),
+            description: None,
+            program: program.to_string(),
+            args,
+            command,
+            env_type: None,
+        }
+    }
+
+    fn with_description(mut self, description: Option<String>) -> Self {
+        self.description = description.filter(|d| !d.is_empty());
+        self
+    }
+
+    fn with_env_type(mut self, env_type: &str) -> Self {
+        self.env_type = Some(env_type.to_string());
+        self
+    }
+}
+
+/// Package manager used to run `package.json` scripts, picked from the
+/// lockfile, then the `packageManager` field, defaulting to npm
+pub fn detect_node_package_manager(dir: &Path, package_json: &serde_json::Value) -> &'static str {
+    let lockfiles: &[(&str, &'static str)] = &[
+        ("pnpm-lock.yaml", "pnpm"),
+        ("yarn.lock", "yarn"),
+        ("bun.lock", "bun"),
+        ("bun.lockb", "bun"),
+        ("package-lock.json", "npm"),
+    ];
+    if let Some((_, manager)) = lockfiles.iter().find(|(file, _)| dir.join(file).is_file()) {
+        return manager;
+    }
+
+    let declared = package_json
+        .get("packageManager")
+        .and_then(|v| v.as_str())
+        .and_then(|v| v.split('@').next())
+        .unwrap_or("");
+    match declared {
+        "pnpm" => "pnpm",
+        "yarn" => "yarn",
+        "bun" => "bun",
+        _ => "npm",
+    }
+}
+
+/// `(name, script)` pairs from the `scripts` table of `package.json`
+pub fn parse_package_json_scripts(package_json: &serde_json::Value) -> Vec<(String, String)> {
+    package_json
+        .get("scripts")
+        .and_then(|v| v.as_object())
+        .map(|scripts| {
+            scripts
+                .iter()
+                .filter_map(|(name, script)| Some((name.clone(), script.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Join backslash-continued lines into one
+fn logical_lines(content: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for line in content.lines() {
+        if let Some(stripped) = line.strip_suffix('\\') {
+            current.push_str(stripped);
+            current.push(' ');
+            continue;
+        }
+        current.push_str(line);
+        lines.push(std::mem::take(&mut current));
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Text of a `## description` comment, as used by self-documenting Makefiles
+fn doc_comment(text: &str) -> Option<String> {
+    text.split_once("##")
+        .map(|(_, doc)| doc.trim().to_string())
+        .filter(|doc| !doc.is_empty())
+}
+
+/// Parsed Makefile contents: runnable targets with their descriptions, plus
+/// the files named by `include` directives
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct MakefileOutline {
+    pub targets: Vec<(String, Option<String>)>,
+    pub includes: Vec<String>,
+}
+
+/// Top-level targets of a Makefile. Pattern rules, special targets such as
+/// `.PHONY`, variable assignments, recipe lines and `define` blocks are
+/// skipped.
+pub fn parse_makefile(content: &str) -> MakefileOutline {
+    let mut outline = MakefileOutline::default();
+    let mut in_define = false;
+    let mut pending_doc: Option<String> = None;
+
+    for line in logical_lines(content) {
+        let trimmed = line.trim();
+        if in_define {
+            if trimmed.starts_with("endef") {
+                in_define = false;
+            }
+            continue;
+        }
+        if line.starts_with('\t') {
+            continue;
+        }
+        if trimmed.starts_with("define ") || trimmed == "define" {
+            in_define = true;
+            continue;
+        }
+        if let Some(comment) = trimmed.strip_prefix('#') {
+            pending_doc = comment
+                .strip_prefix('#')
+                .map(|doc| doc.trim().to_string())
+                .filter(|doc| !doc.is_empty());
+            continue;
+        }
+        if trimmed.is_empty() {
+            pending_doc = None;
+            continue;
+        }
+
+        let doc = doc_comment(&line).or_else(|| pending_doc.take());
+        let code = line.split('#').next().unwrap_or("").trim_end();
+
+        if let Some(caps) = MAKE_INCLUDE_RE.captures(code) {
+            outline.includes.extend(
+                caps[1]
+                    .split_whitespace()
+                    .filter(|file| !file.contains('$') && !file.contains('*'))
+                    .map(str::to_string),
+            );
+            continue;
+        }
+
+        let Some(caps) = MAKE_TARGET_RE.captures(code) else {
+            continue;
+        };
+        // Target-specific variables (`target: VAR := x`) are not rules
+        if code[caps.get(1).map_or(0, |m| m.end())..].contains('=') {
+            continue;
+        }
+        for target in caps[1].split_whitespace() {
+            if target.starts_with('.') || target.contains('%') || target.contains('$') {
+                continue;
+            }
+            if !outline.targets.iter().any(|(name, _)| name == target) {
+                outline.targets.push((target.to_string(), doc.clone()));
+            }
+        }
+    }
+    outline
+}
+
+/// Public recipes of a justfile with their doc comments. Recipes starting
+/// with `_` or marked `[private]` are skipped.
+pub fn parse_justfile(content: &str) -> Vec<(String, Option<String>)> {
+    let mut recipes: Vec<(String, Option<String>)> = Vec::new();
+    let mut pending_doc: Option<String> = None;
+    let mut private = false;
+
+    for line in logical_lines(content) {
+        if line.starts_with([' ', '\t']) {
+            continue;
+        }
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            pending_doc = None;
+            private = false;
+            continue;
+        }
+        if let Some(comment) = trimmed.strip_prefix('#') {
+            if !comment.starts_with('!') {
+                pending_doc = Some(comment.trim().to_string());
+            }
+            continue;
+        }
+        if trimmed.starts_with('[') {
+            private |= trimmed.contains("private");
+            continue;
+        }
+        let keyword = trimmed.split_whitespace().next().unwrap_or("");
+        if matches!(
+            keyword,
+            "set" | "alias" | "export" | "import" | "mod" | "if" | "else"
+        ) {
+            pending_doc = None;
+            continue;
+        }
+
+        let Some(caps) = JUST_RECIPE_RE.captures(trimmed) else {
+            pending_doc = None;
+            private = false;
+            continue;
+        };
+        let name = caps[1].to_string();
+        if !name.starts_with('_') && !private && !recipes.iter().any(|(n, _)| *n == name) {
+            recipes.push((name, pending_doc.take().filter(|d| !d.is_empty())));
+        }
+        pending_doc = None;
+        private = false;
+    }
+    recipes
+}
+
+/// Public tasks of a Taskfile with their `desc`. Tasks marked
+/// `internal: true` are skipped.
+pub fn parse_taskfile(content: &str) -> Vec<(String, Option<String>)> {
+    let Ok(doc) = serde_yaml::from_str::<serde_yaml::Value>(content) else {
+        return Vec::new();
+    };
+    let Some(tasks) = doc.get("tasks").and_then(|v| v.as_mapping()) else {
+        return Vec::new();
+    };
+    tasks
+        .iter()
+        .filter_map(|(name, task)| {
+            let name = name.as_str()?;
+            if task.get("internal").and_then(|v| v.as_bool()) == Some(true) {
+                return None;
+            }
+            let desc = task
+                .get("desc")
+                .or_else(|| task.get("summary"))
+                .and_then(|v| v.as_str())
+                .map(|v| v.trim().to_string());
+            Some((name.to_string(), desc))
+        })
+        .collect()
+}
+
+fn find_first(dir: &Path, names: &[&str]) -> Option<PathBuf> {
+    names
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+async fn package_json_tasks(dir: &Path) -> Vec<ProjectTask> {
+    let path = dir.join("package.json");
+    let Ok(content) = fs::read_file_string(&path).await else {
+        return Vec::new();
+    };
+    let Ok(package_json) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return Vec::new();
+    };
+    let manager = detect_node_package_manager(dir, &package_json);
+    let env_type = if manager == "bun" { "bun" } else { "node" };
+
+    parse_package_json_scripts(&package_json)
+        .into_iter()
+        .map(|(name, script)| {
+            ProjectTask::new(
+                ProjectTaskSource::PackageJson,
+                &name,
+                &path,
+                manager,
+                vec!["run".to_string(), name.clone()],
+            )
+            .with_description(Some(script))
+            .with_env_type(env_type)
+        })
+        .collect()
+}
+
+async fn makefile_tasks(dir: &Path) -> Vec<ProjectTask> {
+    let Some(makefile) = find_first(dir, MAKEFILE_NAMES) else {
+        return Vec::new();
+    };
+
+    let mut tasks: Vec<ProjectTask> = Vec::new();
+    let mut visited = HashSet::new();
+    let mut queue = vec![(makefile, 0usize)];
+    while let Some((file, depth)) = queue.pop() {
+        if depth > MAX_MAKE_INCLUDE_DEPTH || !visited.insert(file.clone()) {
+            continue;
+        }
+        let Ok(content) = fs::read_file_string(&file).await else {
+            continue;
+        };
+        let outline = parse_makefile(&content);
+        for (target, doc) in outline.targets {
+            if tasks.iter().any(|task| task.name == target) {
+                continue;
+            }
+            tasks.push(
+                ProjectTask::new(
+                    ProjectTaskSource::Makefile,
+                    &target,
+                    &file,
+                    "make",
+                    vec![target.clone()],
+                )
+                .with_description(doc),
+            );
+        }
+        // Make resolves includes against its working directory
+        for include in outline.includes.into_iter().rev() {
+            queue.push((dir.join(include), depth + 1));
+        }
+    }
+    tasks
+}
+
+async fn justfile_tasks(dir: &Path) -> Vec<ProjectTask> {
+    let Some(path) = find_first(dir, JUSTFILE_NAMES) else {
+        return Vec::new();
+    };
+    let Ok(content) = fs::read_file_string(&path).await else {
+        return Vec::new();
+    };
+    parse_justfile(&content)
+        .into_iter()
+        .map(|(name, doc)| {
+            ProjectTask::new(
+                ProjectTaskSource::Justfile,
+                &name,
+                &path,
+                "just",
+                vec![name.clone()],
+            )
+            .with_description(doc)
+        })
+        .collect()
+}
+
+async fn taskfile_tasks(dir: &Path) -> Vec<ProjectTask> {
+    let Some(path) = find_first(dir, TASKFILE_NAMES) else {
+        return Vec::new();
+    };
+    let Ok(content) = fs::read_file_string(&path).await else {
+        return Vec::new();
+    };
+    parse_taskfile(&content)
+        .into_iter()
+        .map(|(name, desc)| {
+            ProjectTask::new(
+                ProjectTaskSource::Taskfile,
+                &name,
+                &path,
+                "task",
+                vec![name.clone()],
+            )
+            .with_description(desc)
+        })
+        .collect()
+}
+
+/// Every runnable task declared in `dir`
+pub async fn list_project_tasks(dir: &Path) -> CogniaResult<Vec<ProjectTask>> {
+    if !dir.is_dir() {
+        return Err(CogniaError::ProjectNotFound(dir.display().to_string()));
+    }
+
+    let mut tasks = package_json_tasks(dir).await;
+    tasks.extend(makefile_tasks(dir).await);
+    tasks.extend(justfile_tasks(dir).await);
+    tasks.extend(taskfile_tasks(dir).await);
+    Ok(tasks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_parse_makefile_targets() {
+        let content = "\
+include common.mk
+-include $(CONFIG)/local.mk
+CC := gcc
+FLAGS = -O2 -DX=a:b
+.PHONY: build test
+
+## Build the binary
+build: src/main.c
+\t$(CC) -o app src/main.c   # not a target: here
+
+test lint: build ## Run checks
+\t./run-tests
+
+%.o: %.c
+\t$(CC) -c $<
+
+define RECIPE
+fake: target
+endef
+
+install:: \\
+    build
+\tcp app /usr/local/bin
+";
+        let outline = parse_makefile(content);
+        let names: Vec<&str> = outline.targets.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, ["build", "test", "lint", "install"]);
+        assert_eq!(outline.targets[0].1.as_deref(), Some("Build the binary"));
+        assert_eq!(outline.targets[1].1.as_deref(), Some("Run checks"));
+        assert_eq!(outline.includes, ["common.mk"]);
+    }
+
+    #[test]
+    fn test_parse_justfile_recipes() {
+        let content = "\
+set shell := [\"bash\", \"-c\"]
+version := \"1.0\"
+alias b := build
+
+# Compile everything
+build target='debug':
+    cargo build --profile {{target}}
+
+@test *args: build
+    cargo test {{args}}
+
+_helper:
+    echo hidden
+
+[private]
+internal:
+    echo hidden
+
+[group('ci')]
+lint:
+    cargo clippy
+";
+        let recipes = parse_justfile(content);
+        let names: Vec<&str> = recipes.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, ["build", "test", "lint"]);
+        assert_eq!(recipes[0].1.as_deref(), Some("Compile everything"));
+        assert_eq!(recipes[1].1, None);
+    }
+
+    #[test]
+    fn test_parse_taskfile() {
+        let content = "\
+version: '3'
+tasks:
+  build:
+    desc: Build it
+    cmds: [go build ./...]
+  setup:
+    internal: true
+    cmds: [echo]
+  test:
+    cmds: [go test ./...]
+";
+        let tasks = parse_taskfile(content);
+        assert_eq!(
+            tasks,
+            [
+                ("build".to_string(), Some("Build it".to_string())),
+                ("test".to_string(), None)
+            ]
+        );
+        assert!(parse_taskfile("tasks: [").is_empty());
+    }
+
+    #[test]
+    fn test_detect_node_package_manager_prefers_lockfile() {
+        let dir = tempdir().unwrap();
+        let declared = serde_json::json!({ "packageManager": "yarn@4.1.0" });
+        assert_eq!(detect_node_package_manager(dir.path(), &declared), "yarn");
+
+        std::fs::write(dir.path().join("pnpm-lock.yaml"), "").unwrap();
+        assert_eq!(detect_node_package_manager(dir.path(), &declared), "pnpm");
+        assert_eq!(
+            detect_node_package_manager(tempdir().unwrap().path(), &serde_json::json!({})),
+            "npm"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_project_tasks_namespaces_sources() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(
+            root.join("package.json"),
+            r#"{"scripts":{"build":"tsc","test":"vitest"}}"#,
+        )
+        .unwrap();
+        std::fs::write(root.join("bun.lockb"), "").unwrap();
+        std::fs::write(
+            root.join("Makefile"),
+            "include extra.mk\nbuild:\n\tmake-it\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("extra.mk"), "deploy: build\n\tship\n").unwrap();
+        std::fs::write(root.join("justfile"), "build:\n    just-it\n").unwrap();
+
+        let tasks = list_project_tasks(root).await.unwrap();
+        let ids: Vec<&str> = tasks.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            [
+                "npm:build",
+                "npm:test",
+                "make:build",
+                "make:deploy",
+                "just:build"
+            ]
+        );
+        assert_eq!(tasks[0].command, "bun run build");
+        assert_eq!(tasks[0].env_type.as_deref(), Some("bun"));
+        assert_eq!(tasks[3].command, "make deploy");
+        assert!(tasks[3].source_file.ends_with("extra.mk"));
+    }
+}
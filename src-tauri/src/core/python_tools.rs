@@ -0,0 +1,452 @@
+//! Isolated Python CLI tools installed through pipx or `uv tool`, listed in
+//! one shape whichever backend manages them.
+
+use crate::error::{CogniaError, CogniaResult};
+use serde::{Deserialize, Serialize};
+
+/// Env type of the shims created for Python tool binaries
+pub const PYTHON_TOOL_SHIM_ENV_TYPE: &str = "python-tool";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PythonToolBackend {
+    Pipx,
+    Uv,
+}
+
+impl PythonToolBackend {
+    /// Program name of the backend
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pipx => "pipx",
+            Self::Uv => "uv",
+        }
+    }
+}
+
+impl std::fmt::Display for PythonToolBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Backend requested for a tool operation or configured as the default
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PythonToolBackendChoice {
+    /// uv when installed, otherwise pipx
+    #[default]
+    Auto,
+    Pipx,
+    Uv,
+}
+
+impl PythonToolBackendChoice {
+    pub fn backend(self) -> Option<PythonToolBackend> {
+        match self {
+            Self::Auto => None,
+            Self::Pipx => Some(PythonToolBackend::Pipx),
+            Self::Uv => Some(PythonToolBackend::Uv),
+        }
+    }
+}
+
+impl std::fmt::Display for PythonToolBackendChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Auto => "auto",
+            Self::Pipx => "pipx",
+            Self::Uv => "uv",
+        })
+    }
+}
+
+impl std::str::FromStr for PythonToolBackendChoice {
+    type Err = CogniaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "pipx" => Ok(Self::Pipx),
+            "uv" => Ok(Self::Uv),
+            _ => Err(CogniaError::Config(
+                "Invalid python tool backend. Valid: auto, pipx, uv".into(),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PythonToolBin {
+    /// Command name, without `.exe`
+    pub name: String,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PythonTool {
+    pub name: String,
+    pub version: Option<String>,
+    /// Python version of the tool's virtual environment
+    pub python_version: Option<String>,
+    pub bins: Vec<PythonToolBin>,
+    pub backend: PythonToolBackend,
+    pub venv_path: Option<String>,
+}
+
+impl PythonTool {
+    pub fn bin_names(&self) -> Vec<String> {
+        self.bins.iter().map(|bin| bin.name.clone()).collect()
+    }
+}
+
+/// PEP 503 normalized name, for matching tools across backends
+pub fn normalize_tool_name(name: &str) -> String {
+    name.trim().to_lowercase().replace(['_', '.'], "-")
+}
+
+/// Command name for an executable file name (`black.exe` -> `black`)
+fn command_name(file_name: &str) -> String {
+    let lower = file_name.to_lowercase();
+    match lower.strip_suffix(".exe") {
+        Some(_) => file_name[..file_name.len() - 4].to_string(),
+        None => file_name.to_string(),
+    }
+}
+
+/// Requirement passed to the backend: `name` or `name==version`; versions
+/// that already carry an operator (`>=2`) are appended as-is
+pub fn install_spec(name: &str, version: Option<&str>) -> String {
+    match version.map(str::trim).filter(|v| !v.is_empty()) {
+        None => name.to_string(),
+        Some(version) if version.starts_with(['=', '<', '>', '!', '~']) => {
+            format!("{}{}", name, version)
+        }
+        Some(version) => format!("{}=={}", name, version),
+    }
+}
+
+/// Parse `pipx list --json`
+pub fn parse_pipx_list(output: &str) -> CogniaResult<Vec<PythonTool>> {
+    let json: serde_json::Value = serde_json::from_str(output)
+        .map_err(|e| CogniaError::Parse(format!("Failed to parse pipx list: {}", e)))?;
+    let Some(venvs) = json["venvs"].as_object() else {
+        return Ok(Vec::new());
+    };
+    let home = json["pipx_home"].as_str();
+
+    let mut tools: Vec<PythonTool> = venvs
+        .iter()
+        .map(|(venv, info)| {
+            let metadata = &info["metadata"];
+            let main = &metadata["main_package"];
+            let apps: Vec<&str> = main["apps"]
+                .as_array()
+                .map(|apps| apps.iter().filter_map(|a| a.as_str()).collect())
+                .unwrap_or_default();
+            let paths: Vec<&str> = main["app_paths"]
+                .as_array()
+                .map(|paths| {
+                    paths
+                        .iter()
+                        .filter_map(|p| p["__Path__"].as_str().or_else(|| p.as_str()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let bins = apps
+                .iter()
+                .map(|app| {
+                    let path = paths
+                        .iter()
+                        .find(|p| {
+                            std::path::Path::new(p)
+                                .file_name()
+                                .is_some_and(|f| f.to_string_lossy() == *app)
+                        })
+                        .map(|p| p.to_string())
+                        .unwrap_or_default();
+                    PythonToolBin {
+                        name: command_name(app),
+                        path,
+                    }
+                })
+                .collect();
+            PythonTool {
+                name: main["package"].as_str().unwrap_or(venv).to_string(),
+                version: main["package_version"].as_str().map(|v| v.to_string()),
+                python_version: metadata["python_version"]
+                    .as_str()
+                    .map(|v| v.trim_start_matches("Python ").trim().to_string()),
+                bins,
+                backend: PythonToolBackend::Pipx,
+                venv_path: home.map(|home| {
+                    std::path::Path::new(home)
+                        .join("venvs")
+                        .join(venv)
+                        .display()
+                        .to_string()
+                }),
+            }
+        })
+        .collect();
+    tools.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(tools)
+}
+
+/// `name (path)` -> (`name`, `path`)
+fn split_path_suffix(line: &str) -> (&str, Option<&str>) {
+    match line.strip_suffix(')').and_then(|l| l.split_once(" (")) {
+        Some((head, path)) => (head.trim(), Some(path)),
+        None => (line.trim(), None),
+    }
+}
+
+/// Parse `uv tool list --show-paths`. Python versions are not part of the
+/// output; read them from the tool's `pyvenv.cfg`.
+pub fn parse_uv_tool_list(output: &str) -> Vec<PythonTool> {
+    let mut tools: Vec<PythonTool> = Vec::new();
+    for line in output.lines() {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with("warning:") {
+            continue;
+        }
+        if let Some(entry) = line.strip_prefix("- ") {
+            let (name, path) = split_path_suffix(entry);
+            if let Some(tool) = tools.last_mut() {
+                tool.bins.push(PythonToolBin {
+                    name: command_name(name),
+                    path: path.unwrap_or_default().to_string(),
+                });
+            }
+            continue;
+        }
+        let (head, venv_path) = split_path_suffix(line);
+        let mut parts = head.split_whitespace();
+        let Some(name) = parts.next() else {
+            continue;
+        };
+        let version = parts
+            .next()
+            .and_then(|v| v.strip_prefix('v'))
+            .map(|v| v.to_string());
+        if version.is_none() {
+            // "No tools installed" and other notices
+            continue;
+        }
+        tools.push(PythonTool {
+            name: name.to_string(),
+            version,
+            python_version: None,
+            bins: Vec::new(),
+            backend: PythonToolBackend::Uv,
+            venv_path: venv_path.map(|p| p.to_string()),
+        });
+    }
+    tools
+}
+
+/// Python version recorded in a virtual environment's `pyvenv.cfg`
+pub fn parse_pyvenv_version(content: &str) -> Option<String> {
+    let mut version = None;
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        match key.trim() {
+            "version_info" => return Some(value.trim().to_string()),
+            "version" => version = Some(value.trim().to_string()),
+            _ => {}
+        }
+    }
+    version
+}
+
+/// Backend to run with. An explicitly `requested` backend must be
+/// installed; otherwise the configured `preferred` one is used when
+/// installed, then uv, then pipx.
+pub fn select_backend(
+    requested: PythonToolBackendChoice,
+    preferred: PythonToolBackendChoice,
+    uv_available: bool,
+    pipx_available: bool,
+) -> CogniaResult<PythonToolBackend> {
+    let available = |backend: PythonToolBackend| match backend {
+        PythonToolBackend::Uv => uv_available,
+        PythonToolBackend::Pipx => pipx_available,
+    };
+    if let Some(backend) = requested.backend() {
+        return if available(backend) {
+            Ok(backend)
+        } else {
+            Err(CogniaError::Unsupported(format!(
+                "{} is not installed",
+                backend
+            )))
+        };
+    }
+    preferred
+        .backend()
+        .into_iter()
+        .chain([PythonToolBackend::Uv, PythonToolBackend::Pipx])
+        .find(|backend| available(*backend))
+        .ok_or_else(|| {
+            CogniaError::Unsupported("Installing Python tools requires uv or pipx".into())
+        })
+}
+
+/// Actionable explanation when a tool failed because a dependency had to
+/// be built from source and the toolchain for it is missing
+pub fn build_failure_hint(
+    tool: &str,
+    output: &str,
+    python_version: Option<&str>,
+) -> Option<String> {
+    let lower = output.to_lowercase();
+    let missing = if lower.contains("microsoft visual c++") {
+        "a C/C++ compiler: install the Visual Studio Build Tools with the \"Desktop development with C++\" workload"
+    } else if lower.contains("rust compiler") || lower.contains("cargo, the rust package manager") {
+        "a Rust toolchain: install it from https://rustup.rs"
+    } else if lower.contains("python.h") {
+        "the Python development headers: install python3-dev (Debian/Ubuntu) or python3-devel (Fedora)"
+    } else if [
+        "gcc",
+        "cc1",
+        "clang",
+        "command 'cc' failed",
+        "unable to execute",
+    ]
+    .iter()
+    .any(|needle| lower.contains(needle))
+    {
+        "a C compiler: install build-essential (Debian/Ubuntu), the Xcode Command Line Tools (macOS) or gcc"
+    } else if lower.contains("failed to build")
+        || lower.contains("did not run successfully")
+        || lower.contains("failed building wheel")
+    {
+        "build tools for a package without a prebuilt wheel"
+    } else {
+        return None;
+    };
+    let python = python_version
+        .map(|v| format!("Python {}", v))
+        .unwrap_or_else(|| "the selected Python".to_string());
+    Some(format!(
+        "{} could not be installed: a dependency has no prebuilt wheel for {} and building it needs {}. \
+         Alternatively install a Python version the package publishes wheels for.",
+        tool, python, missing
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pipx_list() {
+        let tools = parse_pipx_list(
+            r#"{
+                "pipx_home": "/home/u/.local/share/pipx",
+                "venvs": {
+                    "black": {
+                        "metadata": {
+                            "main_package": {
+                                "package": "black",
+                                "package_version": "24.2.0",
+                                "apps": ["black", "blackd"],
+                                "app_paths": [
+                                    {"__type__": "Path", "__Path__": "/home/u/.local/share/pipx/venvs/black/bin/black"},
+                                    {"__type__": "Path", "__Path__": "/home/u/.local/share/pipx/venvs/black/bin/blackd"}
+                                ]
+                            },
+                            "python_version": "Python 3.12.1"
+                        }
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(tools.len(), 1);
+        let black = &tools[0];
+        assert_eq!(black.version.as_deref(), Some("24.2.0"));
+        assert_eq!(black.python_version.as_deref(), Some("3.12.1"));
+        assert_eq!(black.bin_names(), vec!["black", "blackd"]);
+        assert_eq!(
+            black.bins[1].path,
+            "/home/u/.local/share/pipx/venvs/black/bin/blackd"
+        );
+        assert_eq!(
+            black.venv_path.as_deref(),
+            Some("/home/u/.local/share/pipx/venvs/black")
+        );
+        assert!(parse_pipx_list(r#"{"venvs": {}}"#).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_uv_tool_list() {
+        let tools = parse_uv_tool_list(
+            "black v24.2.0 (/home/u/.local/share/uv/tools/black)\n\
+             - black (/home/u/.local/bin/black)\n\
+             - blackd (/home/u/.local/bin/blackd)\n\
+             ruff v0.3.0 (C:\\Users\\u\\uv\\tools\\ruff)\n\
+             - ruff.exe (C:\\Users\\u\\.local\\bin\\ruff.exe)\n",
+        );
+        assert_eq!(tools.len(), 2);
+        assert_eq!(tools[0].version.as_deref(), Some("24.2.0"));
+        assert_eq!(tools[0].bin_names(), vec!["black", "blackd"]);
+        assert_eq!(tools[0].bins[0].path, "/home/u/.local/bin/black");
+        assert_eq!(tools[1].bin_names(), vec!["ruff"]);
+        assert_eq!(
+            tools[1].venv_path.as_deref(),
+            Some("C:\\Users\\u\\uv\\tools\\ruff")
+        );
+        assert!(parse_uv_tool_list("No tools installed\n").is_empty());
+        assert_eq!(
+            parse_pyvenv_version("home = /usr/bin\nversion_info = 3.12.1\n").as_deref(),
+            Some("3.12.1")
+        );
+    }
+
+    #[test]
+    fn test_select_backend() {
+        use PythonToolBackendChoice::*;
+        assert_eq!(
+            select_backend(Auto, Auto, true, true).unwrap(),
+            PythonToolBackend::Uv
+        );
+        assert_eq!(
+            select_backend(Auto, Pipx, true, true).unwrap(),
+            PythonToolBackend::Pipx
+        );
+        assert_eq!(
+            select_backend(Auto, Pipx, true, false).unwrap(),
+            PythonToolBackend::Uv
+        );
+        assert!(select_backend(Pipx, Auto, true, false).is_err());
+        assert!(select_backend(Auto, Auto, false, false).is_err());
+        assert_eq!(install_spec("black", Some("24.2.0")), "black==24.2.0");
+        assert_eq!(install_spec("black", Some(">=24")), "black>=24");
+        assert_eq!(install_spec("black", None), "black");
+    }
+
+    #[test]
+    fn test_build_failure_hint() {
+        let hint = build_failure_hint(
+            "pyyaml",
+            "error: command 'gcc' failed: No such file or directory",
+            Some("3.13.0"),
+        )
+        .unwrap();
+        assert!(hint.contains("Python 3.13.0"));
+        assert!(hint.contains("build-essential"));
+        assert!(build_failure_hint(
+            "x",
+            "error: Microsoft Visual C++ 14.0 or greater is required",
+            None
+        )
+        .unwrap()
+        .contains("Visual Studio Build Tools"));
+        assert!(build_failure_hint("x", "No matching distribution found", None).is_none());
+    }
+}
@@ -0,0 +1,823 @@
+//! Release notes between two versions of an environment or package, shown
+//! before an update.
+//!
+//! Version lists and GitHub release bodies are fetched per source and kept in
+//! the metadata cache. A source that cannot be resolved never fails the
+//! request; it degrades to a link-only entry.
+
+use crate::cache::MetadataCache;
+use crate::core::environment::compare_semver;
+use crate::error::{CogniaError, CogniaResult};
+use crate::provider::github::GitHubProvider;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+
+/// How long fetched version lists and release bodies are reused
+pub const RELEASE_NOTES_CACHE_TTL: i64 = 6 * 60 * 60;
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(15);
+/// Versions listed at most; the newest are kept
+const MAX_ENTRIES: usize = 30;
+const MAX_HIGHLIGHTS: usize = 8;
+/// Release body kept per entry, in characters
+const MAX_BODY_CHARS: usize = 8000;
+const SECURITY_KEYWORDS: &[&str] = &["cve-", "security", "vulnerab"];
+/// Headers GitHub's generated notes always have; they say nothing about the release
+const BOILERPLATE_HEADERS: &[&str] = &[
+    "what's changed",
+    "new contributors",
+    "contributors",
+    "commits",
+    "full changelog",
+    "changelog",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReleaseNotesTarget {
+    Env { env_type: String },
+    Package { provider: String, package: String },
+}
+
+impl ReleaseNotesTarget {
+    fn cache_key(&self) -> String {
+        match self {
+            Self::Env { env_type } => format!("env:{}", env_type),
+            Self::Package { provider, package } => format!("{}:{}", provider, package),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReleaseNoteSource {
+    Github,
+    Nodejs,
+    Python,
+    Npm,
+    Crates,
+    Pypi,
+    /// Only a link could be resolved
+    Link,
+}
+
+/// Notes for one version in the update range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseNoteEntry {
+    pub version: String,
+    pub date: Option<String>,
+    pub url: Option<String>,
+    /// Markdown headers of the notes, e.g. `Notable Changes`
+    pub highlights: Vec<String>,
+    /// The release is flagged as a security release or mentions CVEs
+    pub security: bool,
+    /// Release notes markdown, cut to a readable length
+    pub body: Option<String>,
+    pub source: ReleaseNoteSource,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseNotes {
+    pub from_version: String,
+    pub to_version: String,
+    /// Versions after `from_version` up to `to_version`, newest first
+    pub entries: Vec<ReleaseNoteEntry>,
+    /// No entry has notes text, only links
+    pub degraded: bool,
+    pub has_security: bool,
+    /// More versions were in range than listed
+    pub truncated: bool,
+}
+
+/// Versions a source knows about, before release bodies are merged in
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct VersionListing {
+    /// `owner/repo` whose GitHub releases carry the notes
+    github_repo: Option<String>,
+    versions: Vec<ListedVersion>,
+    /// Project-wide changelog, used when a version has no page of its own
+    changelog_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ListedVersion {
+    version: String,
+    date: Option<String>,
+    url: Option<String>,
+    security: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ReleaseSummary {
+    tag: String,
+    body: Option<String>,
+    published_at: Option<String>,
+    prerelease: bool,
+}
+
+/// Where a target's version list comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Source {
+    Nodejs,
+    Python,
+    Npm(String),
+    Crates(String),
+    Pypi(String),
+    /// Versions are the repo's release tags
+    Github {
+        repo: String,
+        changelog_url: Option<String>,
+    },
+    /// Nothing to list; `{version}` and `{major}` are filled in
+    LinkOnly(&'static str),
+}
+
+impl Source {
+    fn kind(&self) -> ReleaseNoteSource {
+        match self {
+            Self::Nodejs => ReleaseNoteSource::Nodejs,
+            Self::Python => ReleaseNoteSource::Python,
+            Self::Npm(_) => ReleaseNoteSource::Npm,
+            Self::Crates(_) => ReleaseNoteSource::Crates,
+            Self::Pypi(_) => ReleaseNoteSource::Pypi,
+            Self::Github { .. } => ReleaseNoteSource::Github,
+            Self::LinkOnly(_) => ReleaseNoteSource::Link,
+        }
+    }
+}
+
+fn resolve_source(target: &ReleaseNotesTarget) -> Option<Source> {
+    let github = |repo: &str, changelog: Option<&str>| Source::Github {
+        repo: repo.to_string(),
+        changelog_url: changelog.map(str::to_string),
+    };
+    match target {
+        ReleaseNotesTarget::Env { env_type } => match env_type.as_str() {
+            "node" | "nodejs" => Some(Source::Nodejs),
+            "python" | "python3" => Some(Source::Python),
+            "rust" => Some(github(
+                "rust-lang/rust",
+                Some("https://github.com/rust-lang/rust/blob/master/RELEASES.md"),
+            )),
+            "deno" => Some(github("denoland/deno", None)),
+            "bun" => Some(github("oven-sh/bun", None)),
+            "go" | "golang" => Some(Source::LinkOnly(
+                "https://go.dev/doc/devel/release#go{version}",
+            )),
+            "php" => Some(Source::LinkOnly(
+                "https://www.php.net/ChangeLog-{major}.php#{version}",
+            )),
+            "ruby" => Some(Source::LinkOnly(
+                "https://www.ruby-lang.org/en/downloads/releases/",
+            )),
+            _ => None,
+        },
+        ReleaseNotesTarget::Package { provider, package } => match provider.as_str() {
+            "npm" | "pnpm" | "yarn" | "bun" => Some(Source::Npm(package.clone())),
+            "cargo" => Some(Source::Crates(package.clone())),
+            "pip" | "pipx" | "uv" | "poetry" => Some(Source::Pypi(package.clone())),
+            "github" => Some(github(package, None)),
+            _ => None,
+        },
+    }
+}
+
+// ── Fetching ────────────────────────────────────────
+
+async fn fetch_json(url: &str) -> CogniaResult<Value> {
+    let response = crate::platform::proxy::get_shared_client()
+        .get(url)
+        .timeout(FETCH_TIMEOUT)
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .map_err(|e| CogniaError::Network(format!("Failed to fetch {}: {}", url, e)))?;
+    if !response.status().is_success() {
+        return Err(CogniaError::Network(format!(
+            "{} returned status {}",
+            url,
+            response.status()
+        )));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| CogniaError::Parse(format!("Invalid JSON from {}: {}", url, e)))
+}
+
+async fn fetch_listing(source: &Source) -> CogniaResult<VersionListing> {
+    Ok(match source {
+        Source::Nodejs => {
+            parse_node_index(&fetch_json("https://nodejs.org/dist/index.json").await?)
+        }
+        Source::Python => parse_python_releases(
+            &fetch_json("https://www.python.org/api/v2/downloads/release/?is_published=true")
+                .await?,
+        ),
+        Source::Npm(name) => {
+            let url = format!(
+                "https://registry.npmjs.org/{}",
+                name.replacen('/', "%2f", 1)
+            );
+            parse_npm_document(name, &fetch_json(&url).await?)
+        }
+        Source::Crates(name) => {
+            let url = format!("https://crates.io/api/v1/crates/{}", name);
+            parse_crates_document(name, &fetch_json(&url).await?)
+        }
+        Source::Pypi(name) => {
+            let url = format!("https://pypi.org/pypi/{}/json", name);
+            parse_pypi_document(name, &fetch_json(&url).await?)
+        }
+        Source::Github {
+            repo,
+            changelog_url,
+        } => VersionListing {
+            github_repo: Some(repo.clone()),
+            versions: Vec::new(),
+            changelog_url: changelog_url.clone(),
+        },
+        Source::LinkOnly(_) => VersionListing::default(),
+    })
+}
+
+async fn fetch_releases(github: &GitHubProvider, repo: &str) -> CogniaResult<Vec<ReleaseSummary>> {
+    Ok(github
+        .list_releases(repo)
+        .await?
+        .into_iter()
+        .filter(|r| !r.draft)
+        .map(|r| ReleaseSummary {
+            tag: r.tag_name,
+            body: r.body,
+            published_at: r.published_at,
+            prerelease: r.prerelease,
+        })
+        .collect())
+}
+
+// ── Parsing ─────────────────────────────────────────
+
+fn str_field(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+/// `YYYY-MM-DD` part of a timestamp
+fn date_only(value: &str) -> String {
+    value.get(..10).unwrap_or(value).to_string()
+}
+
+fn parse_node_index(index: &Value) -> VersionListing {
+    let versions = index
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|release| {
+            let version = str_field(release, "version")?;
+            let version = version.trim_start_matches('v').to_string();
+            Some(ListedVersion {
+                url: Some(format!("https://nodejs.org/en/blog/release/v{}", version)),
+                date: str_field(release, "date"),
+                security: release
+                    .get("security")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false),
+                version,
+            })
+        })
+        .collect();
+    VersionListing {
+        github_repo: Some("nodejs/node".into()),
+        versions,
+        changelog_url: Some("https://github.com/nodejs/node/blob/main/CHANGELOG.md".into()),
+    }
+}
+
+fn parse_python_releases(releases: &Value) -> VersionListing {
+    let versions = releases
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|release| {
+            let name = str_field(release, "name")?;
+            let version = name.strip_prefix("Python ")?.trim().to_string();
+            Some(ListedVersion {
+                date: str_field(release, "release_date").map(|d| date_only(&d)),
+                url: str_field(release, "release_notes_url")
+                    .filter(|u| !u.is_empty())
+                    .or_else(|| {
+                        Some(format!(
+                            "https://docs.python.org/release/{}/whatsnew/changelog.html",
+                            version
+                        ))
+                    }),
+                security: false,
+                version,
+            })
+        })
+        .collect();
+    VersionListing {
+        github_repo: None,
+        versions,
+        changelog_url: Some("https://docs.python.org/3/whatsnew/changelog.html".into()),
+    }
+}
+
+/// `owner/repo` from any GitHub URL form package registries use, e.g.
+/// `git+ssh://git@github.com/owner/repo.git` or `https://github.com/owner/repo#readme`
+fn github_repo_from_url(url: &str) -> Option<String> {
+    let (_, path) = url.split_once("github.com")?;
+    let mut parts = path.trim_start_matches([':', '/']).split('/');
+    let owner = parts.next().filter(|s| !s.is_empty())?;
+    let repo = parts
+        .next()?
+        .split(['#', '?'])
+        .next()?
+        .trim_end_matches(".git");
+    (!repo.is_empty()).then(|| format!("{}/{}", owner, repo))
+}
+
+fn repo_changelog(repo: &Option<String>) -> Option<String> {
+    repo.as_ref()
+        .map(|r| format!("https://github.com/{}/blob/HEAD/CHANGELOG.md", r))
+}
+
+fn parse_npm_document(name: &str, doc: &Value) -> VersionListing {
+    let repository = doc.get("repository").and_then(|r| match r {
+        Value::String(s) => Some(s.clone()),
+        other => str_field(other, "url"),
+    });
+    let github_repo = repository.as_deref().and_then(github_repo_from_url);
+    let versions = doc
+        .get("time")
+        .and_then(Value::as_object)
+        .map(|times| {
+            times
+                .iter()
+                .filter(|(version, _)| *version != "created" && *version != "modified")
+                .map(|(version, time)| ListedVersion {
+                    version: version.clone(),
+                    date: time.as_str().map(date_only),
+                    url: Some(format!(
+                        "https://www.npmjs.com/package/{}/v/{}",
+                        name, version
+                    )),
+                    security: false,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    VersionListing {
+        changelog_url: repo_changelog(&github_repo),
+        github_repo,
+        versions,
+    }
+}
+
+fn parse_crates_document(name: &str, doc: &Value) -> VersionListing {
+    let github_repo = doc
+        .get("crate")
+        .and_then(|c| str_field(c, "repository"))
+        .as_deref()
+        .and_then(github_repo_from_url);
+    let versions = doc
+        .get("versions")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter(|v| !v.get("yanked").and_then(Value::as_bool).unwrap_or(false))
+        .filter_map(|v| {
+            let version = str_field(v, "num")?;
+            Some(ListedVersion {
+                date: str_field(v, "created_at").map(|d| date_only(&d)),
+                url: Some(format!("https://crates.io/crates/{}/{}", name, version)),
+                security: false,
+                version,
+            })
+        })
+        .collect();
+    VersionListing {
+        changelog_url: repo_changelog(&github_repo),
+        github_repo,
+        versions,
+    }
+}
+
+fn parse_pypi_document(name: &str, doc: &Value) -> VersionListing {
+    let project_urls: Vec<(String, String)> = doc
+        .get("info")
+        .and_then(|i| i.get("project_urls"))
+        .and_then(Value::as_object)
+        .map(|urls| {
+            urls.iter()
+                .filter_map(|(k, v)| Some((k.to_lowercase(), v.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+    let find_url = |keys: &[&str]| {
+        project_urls
+            .iter()
+            .find(|(k, _)| keys.iter().any(|key| k.contains(key)))
+            .map(|(_, v)| v.clone())
+    };
+    let github_repo = project_urls
+        .iter()
+        .find_map(|(_, url)| github_repo_from_url(url));
+    let changelog_url = find_url(&["changelog", "changes", "release notes", "history"])
+        .or_else(|| repo_changelog(&github_repo));
+    let versions = doc
+        .get("releases")
+        .and_then(Value::as_object)
+        .map(|releases| {
+            releases
+                .iter()
+                .map(|(version, files)| ListedVersion {
+                    version: version.clone(),
+                    date: files
+                        .as_array()
+                        .and_then(|f| f.first())
+                        .and_then(|f| str_field(f, "upload_time"))
+                        .map(|d| date_only(&d)),
+                    url: Some(format!("https://pypi.org/project/{}/{}/", name, version)),
+                    security: false,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    VersionListing {
+        github_repo,
+        versions,
+        changelog_url,
+    }
+}
+
+/// Version part of a release tag: `v20.1.0`, `bun-v1.1.8` and `pkg@2.0.0`
+/// all start at the first digit after any `@`
+fn tag_version(tag: &str) -> &str {
+    let tag = tag.rsplit_once('@').map_or(tag, |(_, v)| v);
+    tag.find(|c: char| c.is_ascii_digit())
+        .map_or(tag, |i| &tag[i..])
+}
+
+fn is_prerelease(version: &str) -> bool {
+    version.contains('-')
+}
+
+/// Markdown headers of release notes, minus boilerplate and the version title
+pub fn extract_highlights(markdown: &str, version: &str) -> Vec<String> {
+    let mut highlights: Vec<String> = Vec::new();
+    for line in markdown.lines() {
+        let line = line.trim();
+        let level = line.chars().take_while(|&c| c == '#').count();
+        if level == 0 || level > 4 || !line[level..].starts_with(char::is_whitespace) {
+            continue;
+        }
+        let text = line[level..]
+            .trim()
+            .trim_end_matches('#')
+            .replace(['*', '`'], "");
+        let text = text.trim();
+        if text.is_empty()
+            || text.contains(version)
+            || BOILERPLATE_HEADERS.contains(&text.to_lowercase().as_str())
+            || highlights.iter().any(|h| h == text)
+        {
+            continue;
+        }
+        highlights.push(text.to_string());
+        if highlights.len() == MAX_HIGHLIGHTS {
+            break;
+        }
+    }
+    highlights
+}
+
+pub fn mentions_security(text: &str) -> bool {
+    let text = text.to_lowercase();
+    SECURITY_KEYWORDS.iter().any(|k| text.contains(k))
+}
+
+fn truncate_chars(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((i, _)) => format!("{}…", &text[..i]),
+        None => text.to_string(),
+    }
+}
+
+fn fill_link(pattern: &str, version: &str) -> String {
+    let major = version.split('.').next().unwrap_or(version);
+    pattern
+        .replace("{version}", version)
+        .replace("{major}", major)
+}
+
+/// Entries for versions after `from` up to `to`, newest first, and whether
+/// any were dropped to stay under the limit
+fn build_entries(
+    listing: &VersionListing,
+    releases: &[ReleaseSummary],
+    source: ReleaseNoteSource,
+    from: &str,
+    to: &str,
+) -> (Vec<ReleaseNoteEntry>, bool) {
+    let in_range = |version: &str| {
+        compare_semver(version, from) > 0
+            && compare_semver(version, to) <= 0
+            && (!is_prerelease(version) || is_prerelease(to))
+    };
+
+    let mut versions: Vec<ListedVersion> = if listing.versions.is_empty() {
+        // Release tags are the only version list
+        releases
+            .iter()
+            .filter(|r| !r.prerelease || is_prerelease(to))
+            .map(|r| ListedVersion {
+                version: tag_version(&r.tag).to_string(),
+                date: r.published_at.as_deref().map(date_only),
+                url: None,
+                security: false,
+            })
+            .collect()
+    } else {
+        listing.versions.clone()
+    };
+    versions.retain(|v| in_range(&v.version));
+    versions.sort_by(|a, b| compare_semver(&b.version, &a.version).cmp(&0));
+    versions.dedup_by(|a, b| compare_semver(&a.version, &b.version) == 0);
+    let truncated = versions.len() > MAX_ENTRIES;
+    versions.truncate(MAX_ENTRIES);
+
+    let entries = versions
+        .into_iter()
+        .map(|v| {
+            let release = releases
+                .iter()
+                .find(|r| compare_semver(tag_version(&r.tag), &v.version) == 0);
+            let body = release
+                .and_then(|r| r.body.as_deref())
+                .map(str::trim)
+                .filter(|b| !b.is_empty());
+            let release_url = release.and_then(|r| {
+                listing
+                    .github_repo
+                    .as_ref()
+                    .map(|repo| format!("https://github.com/{}/releases/tag/{}", repo, r.tag))
+            });
+            ReleaseNoteEntry {
+                highlights: body
+                    .map(|b| extract_highlights(b, &v.version))
+                    .unwrap_or_default(),
+                security: v.security || body.is_some_and(mentions_security),
+                body: body.map(|b| truncate_chars(b, MAX_BODY_CHARS)),
+                source: if body.is_some() {
+                    ReleaseNoteSource::Github
+                } else {
+                    source
+                },
+                url: v
+                    .url
+                    .or(release_url)
+                    .or_else(|| listing.changelog_url.clone()),
+                date: v.date,
+                version: v.version,
+            }
+        })
+        .collect();
+    (entries, truncated)
+}
+
+/// Release notes for the versions after `from_version` up to `to_version`
+pub async fn get_release_notes(
+    target: &ReleaseNotesTarget,
+    from_version: &str,
+    to_version: &str,
+    cache: &mut MetadataCache,
+    github: &GitHubProvider,
+) -> CogniaResult<ReleaseNotes> {
+    if compare_semver(to_version, from_version) <= 0 {
+        return Err(CogniaError::Config(format!(
+            "{} is not newer than {}",
+            to_version, from_version
+        )));
+    }
+
+    let source = resolve_source(target);
+    let listing = match &source {
+        Some(source) => {
+            let key = format!("release_notes:listing:{}", target.cache_key());
+            match cache.get_or_fetch(&key, || fetch_listing(source)).await {
+                Ok(listing) => listing,
+                Err(e) => {
+                    log::warn!("Release notes listing for {:?} failed: {}", target, e);
+                    VersionListing::default()
+                }
+            }
+        }
+        None => VersionListing::default(),
+    };
+
+    let releases = match &listing.github_repo {
+        Some(repo) => {
+            let key = format!("release_notes:github:{}", repo);
+            match cache
+                .get_or_fetch(&key, || fetch_releases(github, repo))
+                .await
+            {
+                Ok(releases) => releases,
+                Err(e) => {
+                    log::warn!("GitHub releases for {} unavailable: {}", repo, e);
+                    Vec::new()
+                }
+            }
+        }
+        None => Vec::new(),
+    };
+
+    let kind = source
+        .as_ref()
+        .map_or(ReleaseNoteSource::Link, Source::kind);
+    let (mut entries, truncated) =
+        build_entries(&listing, &releases, kind, from_version, to_version);
+    if entries.is_empty() {
+        let url = match &source {
+            Some(Source::LinkOnly(pattern)) => Some(fill_link(pattern, to_version)),
+            _ => listing.changelog_url.clone(),
+        };
+        entries.push(ReleaseNoteEntry {
+            version: to_version.to_string(),
+            date: None,
+            url,
+            highlights: Vec::new(),
+            security: false,
+            body: None,
+            source: ReleaseNoteSource::Link,
+        });
+    }
+
+    Ok(ReleaseNotes {
+        from_version: from_version.to_string(),
+        to_version: to_version.to_string(),
+        degraded: entries.iter().all(|e| e.body.is_none()),
+        has_security: entries.iter().any(|e| e.security),
+        truncated,
+        entries,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_extract_highlights_skips_boilerplate_and_version() {
+        let body = "## 2024-04-10, Version 20.12.2 'Iron' (LTS)\n\
+                    ### Notable Changes\n\
+                    #### **Security fix** for `http`\n\
+                    #123 is an issue reference\n\
+                    ## What's Changed\n\
+                    ### Notable Changes\n";
+        assert_eq!(
+            extract_highlights(body, "20.12.2"),
+            vec!["Notable Changes", "Security fix for http"]
+        );
+    }
+
+    #[test]
+    fn test_mentions_security() {
+        assert!(mentions_security("Fixes CVE-2024-27983"));
+        assert!(mentions_security("This is a Security release"));
+        assert!(!mentions_security("Performance improvements"));
+    }
+
+    #[test]
+    fn test_tag_version() {
+        assert_eq!(tag_version("v20.12.2"), "20.12.2");
+        assert_eq!(tag_version("bun-v1.1.8"), "1.1.8");
+        assert_eq!(tag_version("@scope/pkg@2.0.0"), "2.0.0");
+        assert_eq!(tag_version("1.78.0"), "1.78.0");
+    }
+
+    #[test]
+    fn test_node_index_range_with_release_bodies() {
+        let index = json!([
+            {"version": "v21.0.0", "date": "2023-10-17", "security": false},
+            {"version": "v20.12.2", "date": "2024-04-10", "security": true},
+            {"version": "v20.12.1", "date": "2024-04-03", "security": false},
+            {"version": "v20.12.0", "date": "2024-03-26", "security": false},
+            {"version": "v20.11.0", "date": "2024-01-09", "security": false}
+        ]);
+        let listing = parse_node_index(&index);
+        let releases = vec![ReleaseSummary {
+            tag: "v20.12.1".into(),
+            body: Some("### Notable Changes\nFixes CVE-2024-27982".into()),
+            published_at: None,
+            prerelease: false,
+        }];
+        let (entries, truncated) = build_entries(
+            &listing,
+            &releases,
+            ReleaseNoteSource::Nodejs,
+            "20.11.0",
+            "20.12.2",
+        );
+        assert!(!truncated);
+        let versions: Vec<&str> = entries.iter().map(|e| e.version.as_str()).collect();
+        assert_eq!(versions, vec!["20.12.2", "20.12.1", "20.12.0"]);
+        assert!(entries[0].security);
+        assert_eq!(entries[0].source, ReleaseNoteSource::Nodejs);
+        assert_eq!(
+            entries[0].url.as_deref(),
+            Some("https://nodejs.org/en/blog/release/v20.12.2")
+        );
+        assert_eq!(entries[1].source, ReleaseNoteSource::Github);
+        assert_eq!(entries[1].highlights, vec!["Notable Changes"]);
+        assert!(entries[1].security);
+        assert!(!entries[2].security);
+    }
+
+    #[test]
+    fn test_release_tags_as_version_list_skip_prereleases() {
+        let releases = vec![
+            ReleaseSummary {
+                tag: "v1.44.0-rc.1".into(),
+                body: None,
+                published_at: None,
+                prerelease: true,
+            },
+            ReleaseSummary {
+                tag: "v1.43.1".into(),
+                body: Some("## Fixes".into()),
+                published_at: Some("2024-05-01T00:00:00Z".into()),
+                prerelease: false,
+            },
+        ];
+        let listing = VersionListing {
+            github_repo: Some("denoland/deno".into()),
+            ..Default::default()
+        };
+        let (entries, _) = build_entries(
+            &listing,
+            &releases,
+            ReleaseNoteSource::Github,
+            "1.43.0",
+            "1.44.0",
+        );
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].date.as_deref(), Some("2024-05-01"));
+        assert_eq!(
+            entries[0].url.as_deref(),
+            Some("https://github.com/denoland/deno/releases/tag/v1.43.1")
+        );
+    }
+
+    #[test]
+    fn test_parse_npm_document() {
+        let doc = json!({
+            "repository": {"type": "git", "url": "git+https://github.com/expressjs/express.git"},
+            "time": {
+                "created": "2010-12-29T19:38:25.450Z",
+                "modified": "2024-03-25T00:00:00.000Z",
+                "4.19.2": "2024-03-25T14:50:34.611Z"
+            }
+        });
+        let listing = parse_npm_document("express", &doc);
+        assert_eq!(listing.github_repo.as_deref(), Some("expressjs/express"));
+        assert_eq!(listing.versions.len(), 1);
+        assert_eq!(listing.versions[0].date.as_deref(), Some("2024-03-25"));
+    }
+
+    #[test]
+    fn test_github_repo_from_url() {
+        for url in [
+            "git+https://github.com/expressjs/express.git",
+            "git+ssh://git@github.com/expressjs/express.git",
+            "https://github.com/expressjs/express#readme",
+            "git@github.com:expressjs/express",
+        ] {
+            assert_eq!(
+                github_repo_from_url(url).as_deref(),
+                Some("expressjs/express"),
+                "{}",
+                url
+            );
+        }
+        assert_eq!(github_repo_from_url("https://gitlab.com/a/b"), None);
+    }
+
+    #[test]
+    fn test_fill_link() {
+        assert_eq!(
+            fill_link(
+                "https://www.php.net/ChangeLog-{major}.php#{version}",
+                "8.3.6"
+            ),
+            "https://www.php.net/ChangeLog-8.php#8.3.6"
+        );
+    }
+}
@@ -1,10 +1,37 @@
 use crate::error::{CogniaError, CogniaResult};
+use crate::platform::env::{dirs_home, EnvVarScope, EnvVarTransaction, ShellType};
 use crate::platform::fs;
-use crate::platform::process;
+use crate::platform::process::{self, ProgramCandidate, ProgramFileType};
+use crate::resolver::Version;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+/// `+<version>` handling for version-switch shims. The version must be a
+/// single path component so `+../..` cannot escape `versions/<env_type>`.
+#[cfg(windows)]
+const PS1_VERSION_SWITCH: &str = r#"if ($shimArgs.Count -gt 0 -and "$($shimArgs[0])" -match '^\+([A-Za-z0-9][A-Za-z0-9._-]*)$') {
+    $version = $Matches[1]
+    $env:COGNIA_VERSION = $version
+    $shimArgs = @($shimArgs | Select-Object -Skip 1)
+}
+"#;
+
+#[cfg(unix)]
+const BASH_VERSION_SWITCH: &str = r#"if [[ "$1" =~ ^\+([A-Za-z0-9][A-Za-z0-9._-]*)$ ]]; then
+    version="${BASH_REMATCH[1]}"
+    export COGNIA_VERSION="$version"
+    shift
+fi
+"#;
+
+/// Directory under `root_dir` where version shims touch a
+/// `<env_type>/<version>` marker on every run, so versions used from a plain
+/// shell count as used. Unpinned shims touch `current`.
+pub fn shim_usage_dir(root_dir: &Path) -> PathBuf {
+    root_dir.join("state").join("shim-usage")
+}
+
 /// Shim configuration for a specific environment
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShimConfig {
@@ -12,6 +39,15 @@ pub struct ShimConfig {
     pub binary_name: String,
     pub version: Option<String>,
     pub target_path: PathBuf,
+    /// Run `target_path` itself instead of `versions/<env_type>/<version>`;
+    /// for tools installed outside the versions tree
+    #[serde(default)]
+    pub direct: bool,
+    /// Accept `+<version>` as the first argument to run another installed
+    /// version. Off by default: tools like `cargo +nightly` use the same
+    /// syntax for their own toolchain selection.
+    #[serde(default)]
+    pub version_switch: bool,
 }
 
 /// Shim manager for handling executable shims
@@ -67,14 +103,39 @@ pub async fn create_shim(&mut self, config: ShimConfig) -> CogniaResult<PathBuf>
             self.create_unix_shim(&shim_path, &config).await?;
         }
 
+        if !config.direct {
+            fs::create_dir_all(self.usage_dir().join(&config.env_type)).await?;
+        }
+
         self.configs.insert(config.binary_name.clone(), config);
         self.save_configs().await?;
 
         Ok(shim_path)
     }
 
+    /// Where shims record their runs, next to the shim directory
+    fn usage_dir(&self) -> PathBuf {
+        shim_usage_dir(self.shim_dir.parent().unwrap_or(&self.shim_dir))
+    }
+
     #[cfg(windows)]
     async fn create_windows_shim(&self, shim_path: &Path, config: &ShimConfig) -> CogniaResult<()> {
+        if config.direct {
+            let target = config.target_path.display().to_string();
+            let cmd_content = format!(
+                "@echo off\nsetlocal\nset \"COGNIA_SHIM=1\"\nset \"COGNIA_ENV_TYPE={}\"\n\"{}\" %*\n",
+                config.env_type, target
+            );
+            fs::write_file_string(&shim_path.with_extension("cmd"), &cmd_content).await?;
+            let ps1_content = format!(
+                "#!/usr/bin/env pwsh\n$env:COGNIA_SHIM = \"1\"\n$env:COGNIA_ENV_TYPE = \"{}\"\n& '{}' @args\nexit $LASTEXITCODE\n",
+                config.env_type,
+                target.replace('\'', "''")
+            );
+            fs::write_file_string(&shim_path.with_extension("ps1"), &ps1_content).await?;
+            return Ok(());
+        }
+
         // Create a CMD wrapper script
         let cmd_content = format!(
             r#"@echo off
@@ -82,6 +143,7 @@ async fn create_windows_shim(&self, shim_path: &Path, config: &ShimConfig) -> Co
 set "COGNIA_SHIM=1"
 set "COGNIA_ENV_TYPE={}"
 {}
+(type nul > "%~dp0\..\state\shim-usage\{}\{}") 2>nul
 "%~dp0\..\versions\{}\{}\{}" %*
 "#,
             config.env_type,
@@ -92,6 +154,8 @@ async fn create_windows_shim(&self, shim_path: &Path, config: &ShimConfig) -> Co
                 .unwrap_or_default(),
             config.env_type,
             config.version.as_deref().unwrap_or("current"),
+            config.env_type,
+            config.version.as_deref().unwrap_or("current"),
             config.binary_name,
         );
 
@@ -104,8 +168,11 @@ async fn create_windows_shim(&self, shim_path: &Path, config: &ShimConfig) -> Co
 $env:COGNIA_SHIM = "1"
 $env:COGNIA_ENV_TYPE = "{}"
 {}
-$targetPath = Join-Path $PSScriptRoot "..\versions\{}\{}\{}"
-& $targetPath @args
+$version = "{}"
+$shimArgs = @($args)
+{}try {{ [IO.File]::WriteAllText((Join-Path $PSScriptRoot "..\state\shim-usage\{}\$version"), "") }} catch {{ }}
+$targetPath = Join-Path $PSScriptRoot "..\versions\{}\$version\{}"
+& $targetPath @shimArgs
 exit $LASTEXITCODE
 "#,
             config.env_type,
@@ -114,8 +181,14 @@ async fn create_windows_shim(&self, shim_path: &Path, config: &ShimConfig) -> Co
                 .as_ref()
                 .map(|v| format!("$env:COGNIA_VERSION = \"{}\"", v))
                 .unwrap_or_default(),
-            config.env_type,
             config.version.as_deref().unwrap_or("current"),
+            if config.version_switch {
+                PS1_VERSION_SWITCH
+            } else {
+                ""
+            },
+            config.env_type,
+            config.env_type,
             config.binary_name,
         );
 
@@ -127,23 +200,39 @@ async fn create_windows_shim(&self, shim_path: &Path, config: &ShimConfig) -> Co
 
     #[cfg(unix)]
     async fn create_unix_shim(&self, shim_path: &Path, config: &ShimConfig) -> CogniaResult<()> {
-        let content = format!(
-            r#"#!/bin/bash
+        let content = if config.direct {
+            format!(
+                "#!/bin/bash\nexport COGNIA_SHIM=1\nexport COGNIA_ENV_TYPE=\"{}\"\nexec {} \"$@\"\n",
+                config.env_type,
+                posix_single_quoted(&config.target_path.display().to_string()),
+            )
+        } else {
+            format!(
+                r#"#!/bin/bash
 export COGNIA_SHIM=1
 export COGNIA_ENV_TYPE="{}"
 {}
-exec "${{BASH_SOURCE%/*}}/../versions/{}/{}/{}" "$@"
+version="{}"
+{}{{ : > "${{BASH_SOURCE%/*}}/../state/shim-usage/{}/$version"; }} 2>/dev/null
+exec "${{BASH_SOURCE%/*}}/../versions/{}/$version/{}" "$@"
 "#,
-            config.env_type,
-            config
-                .version
-                .as_ref()
-                .map(|v| format!("export COGNIA_VERSION=\"{}\"", v))
-                .unwrap_or_default(),
-            config.env_type,
-            config.version.as_deref().unwrap_or("current"),
-            config.binary_name,
-        );
+                config.env_type,
+                config
+                    .version
+                    .as_ref()
+                    .map(|v| format!("export COGNIA_VERSION=\"{}\"", v))
+                    .unwrap_or_default(),
+                config.version.as_deref().unwrap_or("current"),
+                if config.version_switch {
+                    BASH_VERSION_SWITCH
+                } else {
+                    ""
+                },
+                config.env_type,
+                config.env_type,
+                config.binary_name,
+            )
+        };
 
         fs::write_file_string(shim_path, &content).await?;
 
@@ -205,6 +294,10 @@ pub async fn update_shim_version(
         Ok(())
     }
 
+    pub fn get_shim(&self, binary_name: &str) -> Option<&ShimConfig> {
+        self.configs.get(binary_name)
+    }
+
     /// List all shims
     pub fn list_shims(&self) -> Vec<&ShimConfig> {
         self.configs.values().collect()
@@ -227,6 +320,23 @@ fn get_shim_path(&self, binary_name: &str) -> PathBuf {
         }
     }
 
+    /// The shim config when `path` is one of this manager's shim files
+    /// (including the `.cmd` / `.ps1` wrappers on Windows)
+    pub fn shim_for_path(&self, path: &Path) -> Option<&ShimConfig> {
+        if path.parent()? != self.shim_dir {
+            return None;
+        }
+        let name = path.file_name()?.to_string_lossy();
+        let binary_name = ["cmd", "ps1"]
+            .iter()
+            .find_map(|ext| {
+                let (stem, found) = name.rsplit_once('.')?;
+                found.eq_ignore_ascii_case(ext).then_some(stem)
+            })
+            .unwrap_or(name.as_ref());
+        self.configs.get(binary_name)
+    }
+
     /// Regenerate all shims
     pub async fn regenerate_all(&mut self) -> CogniaResult<()> {
         let configs: Vec<_> = self.configs.values().cloned().collect();
@@ -235,6 +345,380 @@ pub async fn regenerate_all(&mut self) -> CogniaResult<()> {
         }
         Ok(())
     }
+
+    /// Versions installed under `versions/<env_type>` next to the shim
+    /// directory, newest first. These are what a shim accepts as `+<version>`.
+    pub async fn installed_versions(&self, env_type: &str) -> Vec<String> {
+        let Some(base_dir) = self.shim_dir.parent() else {
+            return Vec::new();
+        };
+        let Ok(mut entries) = tokio::fs::read_dir(base_dir.join("versions").join(env_type)).await
+        else {
+            return Vec::new();
+        };
+
+        let mut versions = Vec::new();
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let is_dir = entry.file_type().await.map(|t| t.is_dir()).unwrap_or(false);
+            let name = entry.file_name().to_string_lossy().to_string();
+            if is_dir && name != "current" && !name.starts_with('.') {
+                versions.push(name);
+            }
+        }
+        sort_versions_desc(&mut versions);
+        versions
+    }
+
+    /// Completion definitions for every version-switch shim, offering the
+    /// installed versions as `+<version>` candidates for the first argument
+    pub async fn generate_completions(&self, shell: InitShell) -> String {
+        let mut configs: Vec<&ShimConfig> = self
+            .configs
+            .values()
+            .filter(|c| c.version_switch && !c.direct)
+            .collect();
+        configs.sort_by(|a, b| a.binary_name.cmp(&b.binary_name));
+
+        let mut shims = Vec::with_capacity(configs.len());
+        for config in configs {
+            let versions = self.installed_versions(&config.env_type).await;
+            shims.push((config.binary_name.clone(), versions));
+        }
+        render_completions(shell, &shims)
+    }
+}
+
+/// Where a Cognia shim found on PATH forwards to
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShimTarget {
+    pub env_type: String,
+    /// Pinned version, `None` when the shim follows `current`
+    pub version: Option<String>,
+    pub target_path: String,
+}
+
+/// A PATH match for a program, with the shim it belongs to if any
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgramMatch {
+    #[serde(flatten)]
+    pub candidate: ProgramCandidate,
+    pub shim: Option<ShimTarget>,
+}
+
+/// Every match for `program` on `path_var` in PATH order, marking the ones
+/// that are shims under `<root_dir>/shims`
+pub async fn program_matches(
+    program: &str,
+    path_var: &std::ffi::OsStr,
+    root_dir: &Path,
+) -> Vec<ProgramMatch> {
+    let candidates = process::which_detailed_in(program, path_var);
+    // Only read existing shim configs; probing must not create the directory
+    let shims = if fs::exists(&root_dir.join("shims")).await {
+        ShimManager::new(root_dir).await.ok()
+    } else {
+        None
+    };
+
+    candidates
+        .into_iter()
+        .map(|mut candidate| {
+            let shim = shims
+                .as_ref()
+                .and_then(|shims| shims.shim_for_path(Path::new(&candidate.path)))
+                .map(|config| ShimTarget {
+                    env_type: config.env_type.clone(),
+                    version: config.version.clone(),
+                    target_path: config.target_path.display().to_string(),
+                });
+            if shim.is_some() {
+                candidate.file_type = ProgramFileType::CogniaShim;
+            }
+            ProgramMatch { candidate, shim }
+        })
+        .collect()
+}
+
+fn sort_versions_desc(versions: &mut [String]) {
+    versions.sort_by(|a, b| match (a.parse::<Version>(), b.parse::<Version>()) {
+        (Ok(a), Ok(b)) => b.cmp(&a),
+        (Ok(_), Err(_)) => std::cmp::Ordering::Less,
+        (Err(_), Ok(_)) => std::cmp::Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    });
+}
+
+/// Shells that get a generated init script and shim completions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InitShell {
+    Bash,
+    Zsh,
+    Fish,
+    Nushell,
+    Elvish,
+    PowerShell,
+}
+
+/// First and last line of the block written into shell profiles
+const INIT_BLOCK_START: &str = "# >>> cognia shims >>>";
+const INIT_BLOCK_END: &str = "# <<< cognia shims <<<";
+
+impl InitShell {
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id.trim().to_ascii_lowercase().as_str() {
+            "bash" => Some(Self::Bash),
+            "zsh" => Some(Self::Zsh),
+            "fish" => Some(Self::Fish),
+            "nushell" | "nu" => Some(Self::Nushell),
+            "elvish" | "elv" => Some(Self::Elvish),
+            "powershell" | "pwsh" => Some(Self::PowerShell),
+            _ => None,
+        }
+    }
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::Bash => "bash",
+            Self::Zsh => "zsh",
+            Self::Fish => "fish",
+            Self::Nushell => "nushell",
+            Self::Elvish => "elvish",
+            Self::PowerShell => "powershell",
+        }
+    }
+
+    /// The user's login shell; `cmd` maps to PowerShell
+    pub fn detect() -> Self {
+        if std::env::var("SHELL").is_ok_and(|shell| shell.contains("elvish")) {
+            return Self::Elvish;
+        }
+        match ShellType::detect() {
+            ShellType::Bash => Self::Bash,
+            ShellType::Zsh => Self::Zsh,
+            ShellType::Fish => Self::Fish,
+            ShellType::Nushell => Self::Nushell,
+            ShellType::PowerShell | ShellType::Cmd => Self::PowerShell,
+        }
+    }
+
+    /// Shells whose profile gets the init block instead of a PATH export,
+    /// because they do not understand `export PATH=...:$PATH`
+    pub fn uses_init_block(&self) -> bool {
+        matches!(self, Self::Fish | Self::Nushell | Self::Elvish)
+    }
+
+    /// Profile file the init script belongs in
+    pub fn config_file(&self) -> Option<PathBuf> {
+        let home = dirs_home()?;
+        match self {
+            Self::Bash => ShellType::Bash.config_file(),
+            Self::Zsh => ShellType::Zsh.config_file(),
+            Self::PowerShell => ShellType::PowerShell.config_file(),
+            Self::Fish => Some(home.join(".config/fish/config.fish")),
+            // PATH changes belong in env.nu, which nushell loads before config.nu
+            Self::Nushell => {
+                #[cfg(windows)]
+                {
+                    std::env::var("APPDATA")
+                        .ok()
+                        .map(|appdata| PathBuf::from(appdata).join("nushell").join("env.nu"))
+                }
+                #[cfg(not(windows))]
+                {
+                    Some(home.join(".config/nushell/env.nu"))
+                }
+            }
+            Self::Elvish => {
+                let legacy = home.join(".elvish/rc.elv");
+                if legacy.exists() {
+                    Some(legacy)
+                } else {
+                    Some(home.join(".config/elvish/rc.elv"))
+                }
+            }
+        }
+    }
+}
+
+fn double_quoted(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn fish_quoted(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('$', "\\$")
+    )
+}
+
+/// Single-quoted string for shells that escape `'` by doubling it
+fn single_quoted(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+#[cfg(unix)]
+fn posix_single_quoted(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Idempotent snippet that puts `shim_dir` on PATH, wrapped in marker lines
+/// so it can be replaced or removed later
+pub fn render_init_script(shell: InitShell, shim_dir: &Path) -> String {
+    let dir = shim_dir.display().to_string();
+    let body = match shell {
+        InitShell::Bash | InitShell::Zsh => {
+            let inner = dir
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('$', "\\$")
+                .replace('`', "\\`");
+            format!(
+                "case \":$PATH:\" in\n  *\":{inner}:\"*) ;;\n  *) export PATH=\"{inner}:$PATH\" ;;\nesac"
+            )
+        }
+        InitShell::Fish => format!("fish_add_path --global --prepend {}", fish_quoted(&dir)),
+        InitShell::Nushell => format!(
+            "$env.PATH = ($env.PATH | split row (char esep) | prepend {} | uniq)",
+            double_quoted(&dir)
+        ),
+        InitShell::Elvish => {
+            let quoted = single_quoted(&dir);
+            format!("if (not (has-value $paths {quoted})) {{ set paths = [{quoted} $@paths] }}")
+        }
+        InitShell::PowerShell => {
+            let quoted = single_quoted(&dir);
+            format!(
+                "if (-not (($env:PATH -split [IO.Path]::PathSeparator) -contains {quoted})) {{\n  $env:PATH = {quoted} + [IO.Path]::PathSeparator + $env:PATH\n}}"
+            )
+        }
+    };
+    format!("{}\n{}\n{}\n", INIT_BLOCK_START, body, INIT_BLOCK_END)
+}
+
+/// Line range of the init block, if the content has a complete one
+fn init_block_range(lines: &[&str]) -> Option<(usize, usize)> {
+    let start = lines.iter().position(|l| l.trim() == INIT_BLOCK_START)?;
+    let end = lines[start..]
+        .iter()
+        .position(|l| l.trim() == INIT_BLOCK_END)?;
+    Some((start, start + end))
+}
+
+/// Replace the init block in `content`, or append it when missing
+pub fn upsert_init_block(content: &str, script: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut output = match init_block_range(&lines) {
+        Some((start, end)) => {
+            let mut updated: Vec<&str> = lines[..start].to_vec();
+            updated.extend(script.lines());
+            updated.extend(&lines[end + 1..]);
+            updated.join("\n")
+        }
+        None if content.trim().is_empty() => script.trim_end().to_string(),
+        None => format!("{}\n\n{}", content.trim_end(), script.trim_end()),
+    };
+    output.push('\n');
+    output
+}
+
+/// Drop the init block (and the blank line left above it) from `content`
+pub fn remove_init_block(content: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let Some((start, end)) = init_block_range(&lines) else {
+        return content.to_string();
+    };
+    let mut kept: Vec<&str> = lines[..start].to_vec();
+    if kept.last().is_some_and(|l| l.trim().is_empty()) {
+        kept.pop();
+    }
+    kept.extend(&lines[end + 1..]);
+    let mut output = kept.join("\n");
+    if !output.is_empty() {
+        output.push('\n');
+    }
+    output
+}
+
+/// Whether `content` carries an init block for `shim_dir`
+pub fn has_init_block(content: &str, shim_dir: &Path) -> bool {
+    let lines: Vec<&str> = content.lines().collect();
+    let dir = shim_dir.display().to_string();
+    init_block_range(&lines)
+        .is_some_and(|(start, end)| lines[start..=end].iter().any(|l| l.contains(&dir)))
+}
+
+/// Shell function names cannot contain every character a binary name can
+fn completion_ident(binary: &str) -> String {
+    binary
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Completion definitions for `(binary, installed versions)` pairs. Only the
+/// first argument is completed; everything after falls back to the shell's
+/// default completion.
+pub fn render_completions(shell: InitShell, shims: &[(String, Vec<String>)]) -> String {
+    let mut out = String::from("# Cognia shim completions (generated)\n");
+    for (binary, versions) in shims {
+        let candidates: Vec<String> = versions.iter().map(|v| format!("+{}", v)).collect();
+        let block = match shell {
+            InitShell::Bash => format!(
+                "_cognia_shim_{ident}() {{\n  if [[ $COMP_CWORD -eq 1 ]]; then\n    COMPREPLY=($(compgen -W {words} -- \"${{COMP_WORDS[1]}}\"))\n  fi\n}}\ncomplete -o default -F _cognia_shim_{ident} {bin}\n",
+                ident = completion_ident(binary),
+                words = single_quoted(&candidates.join(" ")),
+                bin = binary,
+            ),
+            InitShell::Zsh => format!(
+                "_cognia_shim_{ident}() {{\n  _arguments '1::version:({words})' '*::arg:_files'\n}}\ncompdef _cognia_shim_{ident} {bin}\n",
+                ident = completion_ident(binary),
+                words = candidates.join(" "),
+                bin = binary,
+            ),
+            InitShell::Fish => format!(
+                "complete -c {bin} -n 'test (count (commandline -opc)) -eq 1' -a {words} -d 'Installed version'\n",
+                bin = fish_quoted(binary),
+                words = fish_quoted(&candidates.join(" ")),
+            ),
+            InitShell::Nushell => format!(
+                "def \"nu-complete cognia-shim {bin}\" [] {{ [{words}] }}\nexport extern {name} [\n  version?: string@\"nu-complete cognia-shim {bin}\"\n  ...args: string\n]\n",
+                bin = binary,
+                name = double_quoted(binary),
+                words = candidates
+                    .iter()
+                    .map(|c| double_quoted(c))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            ),
+            InitShell::Elvish => format!(
+                "set edit:completion:arg-completer[{bin}] = {{|@words|\n  if (== (count $words) 2) {{ put {words} }} else {{ edit:complete-filename $words[-1] }}\n}}\n",
+                bin = single_quoted(binary),
+                words = candidates
+                    .iter()
+                    .map(|c| single_quoted(c))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            ),
+            InitShell::PowerShell => format!(
+                "Register-ArgumentCompleter -Native -CommandName {bin} -ScriptBlock {{\n  param($wordToComplete, $commandAst, $cursorPosition)\n  if ($commandAst.CommandElements.Count -gt 2) {{ return }}\n  @({words}) | Where-Object {{ $_ -like \"$wordToComplete*\" }} | ForEach-Object {{\n    [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_)\n  }}\n}}\n",
+                bin = single_quoted(binary),
+                words = candidates
+                    .iter()
+                    .map(|c| single_quoted(c))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+        };
+        out.push('\n');
+        out.push_str(&block);
+    }
+    out
 }
 
 /// PATH manager for adding/removing shim directory from system PATH
@@ -262,8 +746,30 @@ pub fn is_in_path(&self) -> bool {
         }
     }
 
+    /// Init script that puts the shim directory on PATH in `shell`
+    pub fn init_script(&self, shell: InitShell) -> String {
+        render_init_script(shell, &self.shim_dir)
+    }
+
+    /// Whether `shell`'s profile already carries the init block for this
+    /// shim directory
+    pub fn is_configured_in_profile(&self, shell: InitShell) -> bool {
+        shell
+            .config_file()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .is_some_and(|content| has_init_block(&content, &self.shim_dir))
+    }
+
     /// Get the command to add shim directory to PATH
     pub fn get_add_to_path_command(&self) -> String {
+        #[cfg(unix)]
+        {
+            let shell = InitShell::detect();
+            if shell.uses_init_block() {
+                return self.add_to_path_command_for(shell);
+            }
+        }
+
         #[cfg(windows)]
         {
             format!(
@@ -289,111 +795,112 @@ pub fn get_add_to_path_command(&self) -> String {
         }
     }
 
-    /// Add shim directory to PATH (requires appropriate permissions)
-    #[cfg(windows)]
-    pub async fn add_to_path(&self) -> CogniaResult<()> {
-        let output = process::execute(
-            "powershell",
-            &[
-                "-Command",
-                &format!(
-                    r#"$currentPath = [Environment]::GetEnvironmentVariable("Path", "User")
-if ($currentPath -notlike "*{}*") {{
-    [Environment]::SetEnvironmentVariable("Path", $currentPath + ";{}", "User")
-}}"#,
-                    self.shim_dir.display(),
-                    self.shim_dir.display()
-                ),
-            ],
-            None,
-        )
-        .await?;
-
-        if !output.success {
-            return Err(CogniaError::Internal(format!(
-                "Failed to add to PATH: {}",
-                output.stderr
-            )));
+    /// One-off command that persists the shim directory on PATH for shells
+    /// configured through an init block
+    #[cfg(unix)]
+    fn add_to_path_command_for(&self, shell: InitShell) -> String {
+        let dir = self.shim_dir.display().to_string();
+        match shell {
+            InitShell::Fish => format!("fish_add_path {}", fish_quoted(&dir)),
+            InitShell::Nushell => format!(
+                "{} | save --append $nu.env-path",
+                double_quoted(&format!("\n{}", self.init_script(shell)))
+            ),
+            _ => {
+                let target = shell
+                    .config_file()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_default();
+                format!(
+                    "printf '\\n%s' {} >> {}",
+                    posix_single_quoted(&self.init_script(shell)),
+                    double_quoted(&target)
+                )
+            }
         }
-
-        Ok(())
     }
 
-    #[cfg(unix)]
+    /// Add shim directory to the user PATH (requires appropriate permissions)
     pub async fn add_to_path(&self) -> CogniaResult<()> {
-        let shell_rc = Self::get_shell_rc();
-        let export_line = format!("export PATH=\"{}:$PATH\"", self.shim_dir.display());
+        self.add_to_path_for(InitShell::detect()).await
+    }
 
-        // Check if already in rc file
-        if let Ok(content) = std::fs::read_to_string(&shell_rc) {
-            if content.contains(&export_line) {
-                return Ok(());
+    /// Add shim directory to PATH for `shell`: shells configured through an
+    /// init block get it in their profile, the others the user PATH.
+    /// Running it again changes nothing.
+    pub async fn add_to_path_for(&self, shell: InitShell) -> CogniaResult<()> {
+        #[cfg(unix)]
+        {
+            if shell.uses_init_block() {
+                return self.write_init_block(shell).await;
             }
         }
+        #[cfg(not(unix))]
+        let _ = shell;
 
-        // Append to rc file
-        use std::io::Write;
-        let mut file = std::fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&shell_rc)?;
-
-        writeln!(file, "\n# Cognia Launcher")?;
-        writeln!(file, "{}", export_line)?;
-
+        EnvVarTransaction::new(EnvVarScope::User)
+            .path_add(self.shim_dir.to_string_lossy(), true)
+            .apply()
+            .await?;
         Ok(())
     }
 
-    #[cfg(unix)]
-    fn get_shell_rc() -> PathBuf {
-        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+    /// Remove shim directory from the user PATH
+    pub async fn remove_from_path(&self) -> CogniaResult<()> {
+        EnvVarTransaction::new(EnvVarScope::User)
+            .path_remove(self.shim_dir.to_string_lossy())
+            .apply()
+            .await?;
 
-        // Try to detect shell
-        if let Ok(shell) = std::env::var("SHELL") {
-            if shell.contains("zsh") {
-                return home.join(".zshrc");
-            } else if shell.contains("fish") {
-                return home.join(".config/fish/config.fish");
-            }
+        #[cfg(unix)]
+        {
+            self.remove_legacy_rc_export()?;
+            self.remove_init_blocks().await?;
         }
 
-        home.join(".bashrc")
+        Ok(())
     }
 
-    /// Remove shim directory from PATH
-    #[cfg(windows)]
-    pub async fn remove_from_path(&self) -> CogniaResult<()> {
-        let output = process::execute(
-            "powershell",
-            &[
-                "-Command",
-                &format!(
-                    r#"$currentPath = [Environment]::GetEnvironmentVariable("Path", "User")
-$newPath = ($currentPath -split ';' | Where-Object {{ $_ -ne "{}" }}) -join ';'
-[Environment]::SetEnvironmentVariable("Path", $newPath, "User")"#,
-                    self.shim_dir.display()
-                ),
-            ],
-            None,
-        )
-        .await?;
-
-        if !output.success {
-            return Err(CogniaError::Internal(format!(
-                "Failed to remove from PATH: {}",
-                output.stderr
-            )));
-        }
+    /// Write the init block into `shell`'s profile, backing up the old file
+    #[cfg(unix)]
+    async fn write_init_block(&self, shell: InitShell) -> CogniaResult<()> {
+        let path = shell.config_file().ok_or_else(|| {
+            CogniaError::Internal(format!("Cannot determine {} config file", shell.id()))
+        })?;
+        let content = std::fs::read_to_string(&path).unwrap_or_default();
+        let updated = upsert_init_block(&content, &self.init_script(shell));
+        crate::platform::env::rewrite_shell_profile(&path, updated).await?;
+        Ok(())
+    }
 
+    /// Remove the init block from every profile that can carry one
+    #[cfg(unix)]
+    async fn remove_init_blocks(&self) -> CogniaResult<()> {
+        for shell in [InitShell::Fish, InitShell::Nushell, InitShell::Elvish] {
+            let Some(path) = shell.config_file() else {
+                continue;
+            };
+            let Ok(content) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            if has_init_block(&content, &self.shim_dir) {
+                crate::platform::env::rewrite_shell_profile(&path, remove_init_block(&content))
+                    .await?;
+            }
+        }
         Ok(())
     }
 
+    /// Older releases appended a standalone export line to the shell rc file.
     #[cfg(unix)]
-    pub async fn remove_from_path(&self) -> CogniaResult<()> {
+    fn remove_legacy_rc_export(&self) -> CogniaResult<()> {
         let shell_rc = Self::get_shell_rc();
         let export_line = format!("export PATH=\"{}:$PATH\"", self.shim_dir.display());
 
         if let Ok(content) = std::fs::read_to_string(&shell_rc) {
+            if !content.contains(&export_line) {
+                return Ok(());
+            }
             let new_content: Vec<_> = content
                 .lines()
                 .filter(|line| !line.contains(&export_line) && !line.contains("# Cognia Launcher"))
@@ -404,6 +911,22 @@ pub async fn remove_from_path(&self) -> CogniaResult<()> {
 
         Ok(())
     }
+
+    #[cfg(unix)]
+    fn get_shell_rc() -> PathBuf {
+        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+
+        // Try to detect shell
+        if let Ok(shell) = std::env::var("SHELL") {
+            if shell.contains("zsh") {
+                return home.join(".zshrc");
+            } else if shell.contains("fish") {
+                return home.join(".config/fish/config.fish");
+            }
+        }
+
+        home.join(".bashrc")
+    }
 }
 
 #[cfg(test)]
@@ -417,6 +940,8 @@ fn test_shim_config() {
             binary_name: "node".into(),
             version: Some("18.0.0".into()),
             target_path: PathBuf::from("/usr/local/bin/node"),
+            direct: false,
+            version_switch: false,
         };
 
         assert_eq!(config.env_type, "node");
@@ -430,6 +955,8 @@ fn test_shim_config_no_version() {
             binary_name: "python3".into(),
             version: None,
             target_path: PathBuf::from("/usr/bin/python3"),
+            direct: false,
+            version_switch: false,
         };
 
         assert_eq!(config.binary_name, "python3");
@@ -443,6 +970,8 @@ fn test_shim_config_serde_roundtrip() {
             binary_name: "node".into(),
             version: Some("20.10.0".into()),
             target_path: PathBuf::from("/home/user/.fnm/node"),
+            direct: false,
+            version_switch: false,
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -460,6 +989,8 @@ fn test_shim_config_serde_no_version() {
             binary_name: "go".into(),
             version: None,
             target_path: PathBuf::from("/usr/local/go/bin/go"),
+            direct: false,
+            version_switch: false,
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -503,16 +1034,59 @@ async fn test_shim_manager_create_and_list() {
             binary_name: "node".into(),
             version: Some("20.0.0".into()),
             target_path: PathBuf::from("/usr/local/bin/node"),
+            direct: false,
+            version_switch: false,
         };
 
         let shim_path = manager.create_shim(config).await.unwrap();
         assert!(!shim_path.to_string_lossy().is_empty());
+        assert!(shim_usage_dir(dir.path()).join("node").is_dir());
+        #[cfg(unix)]
+        assert!(std::fs::read_to_string(&shim_path)
+            .unwrap()
+            .contains("/../state/shim-usage/node/$version\"; } 2>/dev/null"));
 
         let shims = manager.list_shims();
         assert_eq!(shims.len(), 1);
         assert_eq!(shims[0].binary_name, "node");
     }
 
+    #[tokio::test]
+    async fn test_program_matches_marks_shims() {
+        let dir = tempfile::tempdir().unwrap();
+        let other = tempfile::tempdir().unwrap();
+        assert!(
+            program_matches("node", other.path().as_os_str(), dir.path())
+                .await
+                .is_empty()
+        );
+        assert!(!dir.path().join("shims").exists());
+
+        let mut manager = ShimManager::new(dir.path()).await.unwrap();
+        manager
+            .create_shim(ShimConfig {
+                env_type: "node".into(),
+                binary_name: "node".into(),
+                version: Some("20.0.0".into()),
+                target_path: PathBuf::from("/usr/local/bin/node"),
+                direct: false,
+                version_switch: false,
+            })
+            .await
+            .unwrap();
+
+        let path_var = std::env::join_paths([manager.shim_dir(), other.path()]).unwrap();
+        let matches = program_matches("node", &path_var, dir.path()).await;
+        let shim_match = matches
+            .iter()
+            .find(|m| m.shim.is_some())
+            .expect("shim is listed");
+        assert_eq!(shim_match.candidate.file_type, ProgramFileType::CogniaShim);
+        let target = shim_match.shim.as_ref().unwrap();
+        assert_eq!(target.env_type, "node");
+        assert_eq!(target.version.as_deref(), Some("20.0.0"));
+    }
+
     #[tokio::test]
     async fn test_shim_manager_remove() {
         let dir = tempfile::tempdir().unwrap();
@@ -523,6 +1097,8 @@ async fn test_shim_manager_remove() {
             binary_name: "node".into(),
             version: Some("20.0.0".into()),
             target_path: PathBuf::from("/usr/local/bin/node"),
+            direct: false,
+            version_switch: false,
         };
 
         manager.create_shim(config).await.unwrap();
@@ -552,6 +1128,8 @@ async fn test_shim_manager_update_version() {
             binary_name: "node".into(),
             version: Some("18.0.0".into()),
             target_path: PathBuf::from("/usr/local/bin/node"),
+            direct: false,
+            version_switch: false,
         };
 
         manager.create_shim(config).await.unwrap();
@@ -587,6 +1165,8 @@ async fn test_shim_manager_regenerate_all() {
                     binary_name: (*name).into(),
                     version: Some("20.0.0".into()),
                     target_path: PathBuf::from(format!("/usr/local/bin/{}", name)),
+                    direct: false,
+                    version_switch: false,
                 })
                 .await
                 .unwrap();
@@ -611,6 +1191,8 @@ async fn test_shim_manager_configs_persist() {
                     binary_name: "node".into(),
                     version: Some("20.0.0".into()),
                     target_path: PathBuf::from("/usr/local/bin/node"),
+                    direct: false,
+                    version_switch: false,
                 })
                 .await
                 .unwrap();
@@ -635,4 +1217,205 @@ fn test_shim_manager_get_shim_path() {
         assert!(path.to_string_lossy().contains("node"));
         assert!(path.to_string_lossy().contains("shims"));
     }
+
+    #[test]
+    fn test_init_shell_from_id() {
+        assert_eq!(InitShell::from_id("nu"), Some(InitShell::Nushell));
+        assert_eq!(InitShell::from_id("Elvish"), Some(InitShell::Elvish));
+        assert_eq!(InitShell::from_id("pwsh"), Some(InitShell::PowerShell));
+        assert_eq!(InitShell::from_id("cmd"), None);
+    }
+
+    #[test]
+    fn test_render_init_script_per_shell() {
+        let dir = Path::new("/opt/cognia/bin");
+
+        let fish = render_init_script(InitShell::Fish, dir);
+        assert_eq!(
+            fish,
+            "# >>> cognia shims >>>\nfish_add_path --global --prepend \"/opt/cognia/bin\"\n# <<< cognia shims <<<\n"
+        );
+
+        let nu = render_init_script(InitShell::Nushell, dir);
+        assert!(nu.contains("prepend \"/opt/cognia/bin\" | uniq"));
+
+        let elvish = render_init_script(InitShell::Elvish, dir);
+        assert!(elvish.contains("has-value $paths '/opt/cognia/bin'"));
+
+        let bash = render_init_script(InitShell::Bash, dir);
+        assert!(bash.contains("*\":/opt/cognia/bin:\"*) ;;"));
+    }
+
+    #[test]
+    fn test_init_block_upsert_is_idempotent_and_removable() {
+        let dir = Path::new("/opt/cognia/bin");
+        let script = render_init_script(InitShell::Fish, dir);
+        let original = "set -gx EDITOR vim\n";
+
+        let once = upsert_init_block(original, &script);
+        assert_eq!(upsert_init_block(&once, &script), once);
+        assert!(has_init_block(&once, dir));
+        assert!(!has_init_block(&once, Path::new("/elsewhere")));
+
+        // A moved shim directory replaces the block in place
+        let moved = upsert_init_block(
+            &format!("{}echo after\n", once),
+            &render_init_script(InitShell::Fish, Path::new("/new/bin")),
+        );
+        assert_eq!(moved.matches(INIT_BLOCK_START).count(), 1);
+        assert!(moved.contains("/new/bin") && moved.ends_with("echo after\n"));
+
+        assert_eq!(remove_init_block(&once), original);
+        assert_eq!(remove_init_block(original), original);
+    }
+
+    #[test]
+    fn test_render_completions_lists_versions() {
+        let shims = vec![(
+            "node".to_string(),
+            vec!["20.1.0".to_string(), "18.19.0".to_string()],
+        )];
+
+        let fish = render_completions(InitShell::Fish, &shims);
+        assert!(fish.contains(
+            "complete -c \"node\" -n 'test (count (commandline -opc)) -eq 1' -a \"+20.1.0 +18.19.0\""
+        ));
+
+        let nu = render_completions(InitShell::Nushell, &shims);
+        assert!(
+            nu.contains("def \"nu-complete cognia-shim node\" [] { [\"+20.1.0\" \"+18.19.0\"] }")
+        );
+        assert!(nu.contains("export extern \"node\" ["));
+
+        let elvish = render_completions(InitShell::Elvish, &shims);
+        assert!(elvish.contains("set edit:completion:arg-completer['node']"));
+        assert!(elvish.contains("put '+20.1.0' '+18.19.0'"));
+
+        let bash = render_completions(InitShell::Bash, &shims);
+        assert!(bash.contains("complete -o default -F _cognia_shim_node node"));
+    }
+
+    #[tokio::test]
+    async fn test_installed_versions_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = ShimManager::new(dir.path()).await.unwrap();
+        for version in ["18.19.0", "20.1.0", "current", "lts-iron"] {
+            std::fs::create_dir_all(dir.path().join("versions/node").join(version)).unwrap();
+        }
+
+        assert_eq!(
+            manager.installed_versions("node").await,
+            vec!["20.1.0", "18.19.0", "lts-iron"]
+        );
+        assert!(manager.installed_versions("python").await.is_empty());
+    }
+
+    #[cfg(unix)]
+    async fn run_shim(shim: &Path, args: &[&str]) -> String {
+        let out = tokio::process::Command::new("bash")
+            .arg(shim)
+            .args(args)
+            .output()
+            .await
+            .unwrap();
+        String::from_utf8_lossy(&out.stdout).trim().to_string()
+    }
+
+    #[cfg(unix)]
+    async fn fake_version(base: &Path, env_type: &str, version: &str, binary: &str) {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = base.join("versions").join(env_type).join(version);
+        std::fs::create_dir_all(&dir).unwrap();
+        let bin = dir.join(binary);
+        std::fs::write(&bin, format!("#!/bin/sh\necho {} \"$@\"\n", version)).unwrap();
+        std::fs::set_permissions(&bin, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unix_shim_accepts_version_override() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manager = ShimManager::new(dir.path()).await.unwrap();
+        fake_version(dir.path(), "node", "20.1.0", "node").await;
+        fake_version(dir.path(), "node", "18.19.0", "node").await;
+        let shim_path = manager
+            .create_shim(ShimConfig {
+                env_type: "node".into(),
+                binary_name: "node".into(),
+                version: Some("20.1.0".into()),
+                target_path: PathBuf::from("/usr/local/bin/node"),
+                direct: false,
+                version_switch: true,
+            })
+            .await
+            .unwrap();
+
+        let content = std::fs::read_to_string(&shim_path).unwrap();
+        assert!(content.contains("version=\"20.1.0\""));
+        assert!(content.contains("/../versions/node/$version/node\" \"$@\""));
+        assert_eq!(
+            run_shim(&shim_path, &["+18.19.0", "-v"]).await,
+            "18.19.0 -v"
+        );
+        // Not a single path component, so it is passed through untouched
+        assert_eq!(
+            run_shim(&shim_path, &["+../..", "-v"]).await,
+            "20.1.0 +../.. -v"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unix_shim_passes_plus_args_through_by_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manager = ShimManager::new(dir.path()).await.unwrap();
+        fake_version(dir.path(), "rust", "1.80.0", "cargo").await;
+        let shim_path = manager
+            .create_shim(ShimConfig {
+                env_type: "rust".into(),
+                binary_name: "cargo".into(),
+                version: Some("1.80.0".into()),
+                target_path: PathBuf::from("/usr/local/bin/cargo"),
+                direct: false,
+                version_switch: false,
+            })
+            .await
+            .unwrap();
+
+        assert!(!std::fs::read_to_string(&shim_path)
+            .unwrap()
+            .contains("COGNIA_VERSION=\"$version\""));
+        assert_eq!(
+            run_shim(&shim_path, &["+nightly", "build"]).await,
+            "1.80.0 +nightly build"
+        );
+        assert!(manager
+            .generate_completions(InitShell::Bash)
+            .await
+            .lines()
+            .all(|l| !l.contains("cargo")));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unix_direct_shim_runs_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manager = ShimManager::new(dir.path()).await.unwrap();
+        let shim_path = manager
+            .create_shim(ShimConfig {
+                env_type: "python-tool".into(),
+                binary_name: "black".into(),
+                version: None,
+                target_path: PathBuf::from("/home/u/.local/bin/black"),
+                direct: true,
+                version_switch: false,
+            })
+            .await
+            .unwrap();
+
+        let content = std::fs::read_to_string(shim_path).unwrap();
+        assert!(content.contains("exec '/home/u/.local/bin/black' \"$@\""));
+        assert!(!content.contains("versions/"));
+        assert!(manager.get_shim("black").unwrap().direct);
+    }
 }
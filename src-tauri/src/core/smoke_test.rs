@@ -0,0 +1,623 @@
+//! Post-install smoke tests.
+//!
+//! After a version is installed, a short script proves the toolchain actually
+//! runs: built-in checks for the environment type followed by the user's
+//! `general.smoke_tests`. Each command runs without a shell in a scratch
+//! directory, with the version's activation environment applied. Custom
+//! commands are gated like command-based detection rules: they only run when
+//! allowlisted or approved for their exact command line.
+
+use crate::config::SmokeTestDefinition;
+use crate::core::custom_detection::{
+    command_signature, is_allowlisted_command, COMMAND_EXTRACTION_TIMEOUT,
+};
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::env::EnvModifications;
+use crate::platform::fs;
+use crate::platform::process::{self, ProcessError, ProcessOptions, ProcessOutput};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// Timeout for built-in checks that compile a hello-world project
+const BUILD_TIMEOUT: Duration = Duration::from_secs(60);
+
+const GO_HELLO: &str =
+    "package main\n\nimport \"fmt\"\n\nfunc main() {\n\tfmt.Println(\"hello\")\n}\n";
+
+static LOG_MUTATION_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// One command of a smoke test script
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SmokeCheck {
+    name: String,
+    program: String,
+    args: Vec<String>,
+    /// Directory relative to the scratch directory to run in
+    dir: Option<&'static str>,
+    expected_output: Option<String>,
+    expected_exit_code: i32,
+    timeout: Duration,
+    builtin: bool,
+    /// Allowlisted or approved; custom commands that are not get skipped
+    permitted: bool,
+}
+
+impl SmokeCheck {
+    fn builtin(program: &str, args: &[&str]) -> Self {
+        Self {
+            name: command_line(program, args.iter().copied()),
+            program: program.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            dir: None,
+            expected_output: None,
+            expected_exit_code: 0,
+            timeout: COMMAND_EXTRACTION_TIMEOUT,
+            builtin: true,
+            permitted: true,
+        }
+    }
+
+    fn custom(definition: &SmokeTestDefinition) -> Self {
+        let line = command_line(
+            &definition.command,
+            definition.args.iter().map(String::as_str),
+        );
+        Self {
+            name: definition.name.clone().unwrap_or(line),
+            program: definition.command.trim().to_string(),
+            args: definition.args.clone(),
+            dir: None,
+            expected_output: definition.expected_output.clone(),
+            expected_exit_code: definition.expected_exit_code.unwrap_or(0),
+            timeout: COMMAND_EXTRACTION_TIMEOUT,
+            builtin: false,
+            permitted: definition.command_permitted(),
+        }
+    }
+
+    fn expect_output(mut self, expected: &str) -> Self {
+        self.expected_output = Some(expected.to_string());
+        self
+    }
+
+    fn in_dir(mut self, dir: &'static str) -> Self {
+        self.dir = Some(dir);
+        self
+    }
+
+    fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+impl SmokeTestDefinition {
+    /// Canonical command line used to bind user approval to the exact invocation
+    pub fn command_signature(&self) -> String {
+        command_signature(self.command.trim(), &self.args)
+    }
+
+    /// Whether the command may run: allowlisted programs or an approval that
+    /// matches the current command line
+    pub fn command_permitted(&self) -> bool {
+        is_allowlisted_command(self.command.trim(), &self.args)
+            || self.approved_command.as_deref() == Some(self.command_signature().as_str())
+    }
+}
+
+/// Keeps the approvals in `tests` only for command lines `previous` already
+/// approved, so edited or imported tests never arrive pre-approved.
+pub fn retain_approvals(tests: &mut [SmokeTestDefinition], previous: &[SmokeTestDefinition]) {
+    for test in tests {
+        let signature = test.command_signature();
+        let approved = previous
+            .iter()
+            .any(|old| old.approved_command.as_deref() == Some(signature.as_str()));
+        test.approved_command = approved.then_some(signature);
+    }
+}
+
+fn command_line<'a>(program: &str, args: impl Iterator<Item = &'a str>) -> String {
+    std::iter::once(program.trim())
+        .chain(args)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Files written to the scratch directory and the checks run against them
+#[derive(Debug, Default)]
+struct SmokeScript {
+    files: Vec<(&'static str, &'static str)>,
+    checks: Vec<SmokeCheck>,
+}
+
+fn builtin_script(env_type: &str) -> SmokeScript {
+    let npm = if cfg!(windows) { "npm.cmd" } else { "npm" };
+    match env_type {
+        "node" => SmokeScript {
+            files: Vec::new(),
+            checks: vec![
+                SmokeCheck::builtin("node", &["-e", "console.log(1+1)"]).expect_output("2"),
+                SmokeCheck::builtin(npm, &["--version"]),
+            ],
+        },
+        "python" => SmokeScript {
+            files: Vec::new(),
+            checks: vec![SmokeCheck::builtin(
+                "python",
+                &["-c", "import ssl, sqlite3"],
+            )],
+        },
+        "rust" => SmokeScript {
+            files: Vec::new(),
+            checks: vec![
+                SmokeCheck::builtin("cargo", &["new", "--vcs", "none", "--quiet", "hello"]),
+                SmokeCheck::builtin("cargo", &["build", "--quiet", "--offline"])
+                    .in_dir("hello")
+                    .with_timeout(BUILD_TIMEOUT),
+            ],
+        },
+        "go" => SmokeScript {
+            files: vec![("hello.go", GO_HELLO)],
+            checks: vec![
+                SmokeCheck::builtin("go", &["build", "hello.go"]).with_timeout(BUILD_TIMEOUT)
+            ],
+        },
+        _ => SmokeScript::default(),
+    }
+}
+
+/// Built-in checks for `env_type` followed by the custom tests that target it
+fn smoke_script(env_type: &str, custom: &[SmokeTestDefinition]) -> SmokeScript {
+    let mut script = builtin_script(env_type);
+    script.checks.extend(
+        custom
+            .iter()
+            .filter(|test| test.env_type.trim().eq_ignore_ascii_case(env_type))
+            .map(SmokeCheck::custom),
+    );
+    script
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SmokeTestResult {
+    pub name: String,
+    pub command: String,
+    pub builtin: bool,
+    pub passed: bool,
+    /// Not run because the command is neither allowlisted nor approved
+    #[serde(default)]
+    pub skipped: bool,
+    pub exit_code: Option<i32>,
+    pub duration_ms: u64,
+    /// Why the check failed
+    pub message: Option<String>,
+}
+
+/// Outcome of the smoke tests for one installed version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SmokeTestReport {
+    pub env_type: String,
+    pub version: String,
+    pub passed: bool,
+    pub results: Vec<SmokeTestResult>,
+    pub ran_at: String,
+}
+
+fn evaluate(
+    check: &SmokeCheck,
+    outcome: Result<ProcessOutput, ProcessError>,
+    duration_ms: u64,
+) -> SmokeTestResult {
+    let (exit_code, failure) = match outcome {
+        Err(ProcessError::Timeout(timeout)) => (
+            None,
+            Some(format!("timed out after {}s", timeout.as_secs())),
+        ),
+        Err(e) => (None, Some(format!("failed to start: {}", e))),
+        Ok(output) => {
+            let failure = if output.exit_code != check.expected_exit_code {
+                let detail = output
+                    .stderr
+                    .lines()
+                    .map(str::trim)
+                    .find(|line| !line.is_empty());
+                Some(match detail {
+                    Some(detail) => format!(
+                        "exited with {} (expected {}): {}",
+                        output.exit_code, check.expected_exit_code, detail
+                    ),
+                    None => format!(
+                        "exited with {} (expected {})",
+                        output.exit_code, check.expected_exit_code
+                    ),
+                })
+            } else {
+                check.expected_output.as_ref().and_then(|expected| {
+                    let found =
+                        output.stdout.contains(expected) || output.stderr.contains(expected);
+                    (!found).then(|| format!("output did not contain `{}`", expected))
+                })
+            };
+            (Some(output.exit_code), failure)
+        }
+    };
+
+    SmokeTestResult {
+        name: check.name.clone(),
+        command: command_line(&check.program, check.args.iter().map(String::as_str)),
+        builtin: check.builtin,
+        passed: failure.is_none(),
+        skipped: false,
+        exit_code,
+        duration_ms,
+        message: failure,
+    }
+}
+
+/// Process options carrying the activation environment of the version
+fn activated_options(env: &EnvModifications, cwd: &Path, timeout: Duration) -> ProcessOptions {
+    let mut options = ProcessOptions::new()
+        .with_timeout(timeout)
+        .with_cwd(cwd.to_string_lossy().to_string());
+
+    let current_path = std::env::var_os("PATH").unwrap_or_default();
+    let paths: Vec<PathBuf> = env
+        .path_prepend
+        .iter()
+        .cloned()
+        .chain(std::env::split_paths(&current_path))
+        .chain(env.path_append.iter().cloned())
+        .collect();
+    if let Ok(joined) = std::env::join_paths(paths) {
+        options = options.with_env("PATH", joined.to_string_lossy().to_string());
+    }
+    for (key, value) in &env.set_variables {
+        options = options.with_env(key, value);
+    }
+    options
+}
+
+/// Run the smoke tests for a freshly installed `env_type@version`
+pub async fn run_smoke_tests(
+    env_type: &str,
+    version: &str,
+    env: &EnvModifications,
+    custom: &[SmokeTestDefinition],
+) -> CogniaResult<SmokeTestReport> {
+    let script = smoke_script(env_type, custom);
+    let scratch = tempfile::Builder::new()
+        .prefix("cognia-smoke-")
+        .tempdir()
+        .map_err(|e| CogniaError::Internal(format!("Failed to create smoke test dir: {}", e)))?;
+    for (name, content) in &script.files {
+        fs::write_file_string(scratch.path().join(name), content).await?;
+    }
+
+    let mut results = Vec::with_capacity(script.checks.len());
+    for check in &script.checks {
+        if !check.permitted {
+            results.push(SmokeTestResult {
+                name: check.name.clone(),
+                command: command_line(&check.program, check.args.iter().map(String::as_str)),
+                builtin: false,
+                passed: false,
+                skipped: true,
+                exit_code: None,
+                duration_ms: 0,
+                message: Some(
+                    "not run: the command is not allowlisted and has not been approved".into(),
+                ),
+            });
+            continue;
+        }
+        let cwd = match check.dir {
+            Some(dir) => scratch.path().join(dir),
+            None => scratch.path().to_path_buf(),
+        };
+        let args: Vec<&str> = check.args.iter().map(String::as_str).collect();
+        let options = activated_options(env, &cwd, check.timeout);
+        let started = Instant::now();
+        let outcome = process::execute(&check.program, &args, Some(options)).await;
+        results.push(evaluate(
+            check,
+            outcome,
+            started.elapsed().as_millis() as u64,
+        ));
+    }
+
+    Ok(SmokeTestReport {
+        env_type: env_type.to_string(),
+        version: version.to_string(),
+        passed: results.iter().all(|result| result.passed || result.skipped),
+        results,
+        ran_at: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+/// Latest smoke test report per installed version, kept so versions whose
+/// tests failed can be flagged as unverified.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SmokeTestLog {
+    /// `env_type:version` -> latest report
+    #[serde(default)]
+    pub reports: HashMap<String, SmokeTestReport>,
+}
+
+impl SmokeTestLog {
+    fn log_path() -> Option<PathBuf> {
+        fs::get_cognia_dir().map(|dir| dir.join("state").join("smoke-tests.json"))
+    }
+
+    fn report_key(env_type: &str, version: &str) -> String {
+        format!("{}:{}", env_type, version.trim_start_matches('v'))
+    }
+
+    pub async fn load() -> CogniaResult<Self> {
+        let path = Self::log_path()
+            .ok_or_else(|| CogniaError::Config("Could not determine smoke test log path".into()))?;
+        if !fs::exists(&path).await {
+            return Ok(Self::default());
+        }
+        let content = fs::read_file_string(&path).await?;
+        Ok(serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::warn!("Ignoring malformed smoke test log at {:?}: {}", path, e);
+            Self::default()
+        }))
+    }
+
+    pub async fn save(&self) -> CogniaResult<()> {
+        let path = Self::log_path()
+            .ok_or_else(|| CogniaError::Config("Could not determine smoke test log path".into()))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let content = serde_json::to_string(self).map_err(|e| {
+            CogniaError::Config(format!("Failed to serialize smoke test log: {}", e))
+        })?;
+        fs::write_file_atomic(&path, content.as_bytes()).await?;
+        Ok(())
+    }
+
+    pub fn report(&self, env_type: &str, version: &str) -> Option<&SmokeTestReport> {
+        self.reports.get(&Self::report_key(env_type, version))
+    }
+
+    /// Whether the latest smoke tests of `env_type@version` failed
+    pub fn is_unverified(&self, env_type: &str, version: &str) -> bool {
+        self.report(env_type, version)
+            .is_some_and(|report| !report.passed)
+    }
+
+    pub fn insert(&mut self, report: SmokeTestReport) {
+        self.reports
+            .insert(Self::report_key(&report.env_type, &report.version), report);
+    }
+
+    /// Store `report` as the latest result for its version. Failures are
+    /// logged, never surfaced, so bookkeeping can't fail an install.
+    pub async fn record(report: &SmokeTestReport) {
+        let _guard = LOG_MUTATION_LOCK.lock().await;
+        let result = async {
+            let mut log = Self::load().await?;
+            log.insert(report.clone());
+            log.save().await
+        }
+        .await;
+        if let Err(e) = result {
+            log::debug!(
+                "Failed to record smoke tests for {}@{}: {}",
+                report.env_type,
+                report.version,
+                e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(exit_code: i32, stdout: &str, stderr: &str) -> ProcessOutput {
+        ProcessOutput {
+            exit_code,
+            stdout: stdout.to_string(),
+            stderr: stderr.to_string(),
+            success: exit_code == 0,
+        }
+    }
+
+    fn definition(env_type: &str, command: &str) -> SmokeTestDefinition {
+        SmokeTestDefinition {
+            env_type: env_type.to_string(),
+            name: None,
+            command: command.to_string(),
+            args: vec!["--version".to_string()],
+            expected_output: None,
+            expected_exit_code: None,
+            approved_command: None,
+        }
+    }
+
+    #[test]
+    fn test_builtin_scripts_per_env_type() {
+        let node = builtin_script("node");
+        assert_eq!(node.checks.len(), 2);
+        assert_eq!(node.checks[0].expected_output.as_deref(), Some("2"));
+
+        let rust = builtin_script("rust");
+        assert_eq!(rust.checks[1].dir, Some("hello"));
+        assert_eq!(rust.checks[1].timeout, BUILD_TIMEOUT);
+
+        let go = builtin_script("go");
+        assert_eq!(go.files, vec![("hello.go", GO_HELLO)]);
+        assert_eq!(go.checks[0].args, vec!["build", "hello.go"]);
+
+        assert!(builtin_script("zig").checks.is_empty());
+    }
+
+    #[test]
+    fn test_custom_tests_follow_builtins_for_matching_env() {
+        let custom = vec![
+            definition("Python", "pip"),
+            definition("node", "npx"),
+            SmokeTestDefinition {
+                name: Some("venv".to_string()),
+                ..definition("python", "python")
+            },
+        ];
+        let script = smoke_script("python", &custom);
+        let names: Vec<&str> = script.checks.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["python -c import ssl, sqlite3", "pip --version", "venv"]
+        );
+        assert!(script.checks[0].builtin);
+        assert!(!script.checks[1].builtin);
+        assert_eq!(script.checks[1].timeout, COMMAND_EXTRACTION_TIMEOUT);
+    }
+
+    #[test]
+    fn test_evaluate_checks_exit_code_and_output() {
+        let check = SmokeCheck::builtin("node", &["-e", "console.log(1+1)"]).expect_output("2");
+        let passed = evaluate(&check, Ok(output(0, "2\n", "")), 12);
+        assert!(passed.passed);
+        assert_eq!(passed.exit_code, Some(0));
+        assert_eq!(passed.command, "node -e console.log(1+1)");
+
+        let wrong_output = evaluate(&check, Ok(output(0, "3\n", "")), 12);
+        assert_eq!(
+            wrong_output.message.as_deref(),
+            Some("output did not contain `2`")
+        );
+
+        let crashed = evaluate(&check, Ok(output(1, "", "\nReferenceError: x\n")), 12);
+        assert_eq!(
+            crashed.message.as_deref(),
+            Some("exited with 1 (expected 0): ReferenceError: x")
+        );
+
+        let custom = SmokeCheck::custom(&SmokeTestDefinition {
+            expected_exit_code: Some(2),
+            ..definition("node", "node")
+        });
+        assert!(evaluate(&custom, Ok(output(2, "", "")), 1).passed);
+
+        let timed_out = evaluate(
+            &check,
+            Err(ProcessError::Timeout(Duration::from_secs(10))),
+            10_000,
+        );
+        assert!(!timed_out.passed);
+        assert_eq!(timed_out.exit_code, None);
+        assert_eq!(timed_out.message.as_deref(), Some("timed out after 10s"));
+    }
+
+    #[test]
+    fn test_activated_options_prepend_path_and_set_variables() {
+        let env = EnvModifications::new()
+            .prepend_path("/envs/go/1.22/bin")
+            .set_var("GOROOT", "/envs/go/1.22");
+        let options = activated_options(&env, Path::new("/tmp/smoke"), BUILD_TIMEOUT);
+
+        let path = options.env.get("PATH").unwrap();
+        let first = std::env::split_paths(path).next().unwrap();
+        assert_eq!(first, PathBuf::from("/envs/go/1.22/bin"));
+        assert_eq!(options.env.get("GOROOT").unwrap(), "/envs/go/1.22");
+        assert_eq!(options.timeout, Some(BUILD_TIMEOUT));
+        assert_eq!(options.cwd.as_deref(), Some("/tmp/smoke"));
+    }
+
+    #[test]
+    fn test_log_marks_failed_versions_unverified() {
+        let report = |version: &str, passed: bool| SmokeTestReport {
+            env_type: "node".to_string(),
+            version: version.to_string(),
+            passed,
+            results: Vec::new(),
+            ran_at: "2024-01-01T00:00:00Z".to_string(),
+        };
+        let mut log = SmokeTestLog::default();
+        log.insert(report("20.11.0", false));
+        log.insert(report("18.19.0", true));
+
+        assert!(log.is_unverified("node", "v20.11.0"));
+        assert!(!log.is_unverified("node", "18.19.0"));
+        assert!(!log.is_unverified("node", "22.0.0"));
+
+        log.insert(report("20.11.0", true));
+        assert!(!log.is_unverified("node", "20.11.0"));
+    }
+
+    #[test]
+    fn test_custom_checks_need_allowlist_or_matching_approval() {
+        assert!(SmokeCheck::custom(&definition("node", "node")).permitted);
+
+        let mut test = definition("node", "./check.sh");
+        assert!(!SmokeCheck::custom(&test).permitted);
+        test.approved_command = Some(test.command_signature());
+        assert!(SmokeCheck::custom(&test).permitted);
+        test.args.push("--all".into());
+        assert!(!SmokeCheck::custom(&test).permitted);
+    }
+
+    #[test]
+    fn test_retain_approvals_only_for_previously_approved_commands() {
+        let mut approved = definition("node", "./check.sh");
+        approved.approved_command = Some(approved.command_signature());
+        let mut forged = definition("node", "./other.sh");
+        forged.approved_command = Some(forged.command_signature());
+
+        let mut tests = vec![definition("node", "./check.sh"), forged];
+        retain_approvals(&mut tests, &[approved]);
+        assert!(tests[0].command_permitted());
+        assert!(!tests[1].command_permitted());
+        assert!(tests[1].approved_command.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_run_smoke_tests_skips_unapproved_commands() {
+        let custom = vec![definition("zig", "cognia-unapproved-smoke-binary")];
+        let report = run_smoke_tests("zig", "0.12.0", &EnvModifications::new(), &custom)
+            .await
+            .unwrap();
+        let skipped = &report.results[0];
+        assert!(skipped.skipped);
+        assert!(!skipped.passed);
+        assert_eq!(skipped.exit_code, None);
+        assert!(report.passed);
+    }
+
+    #[tokio::test]
+    async fn test_run_smoke_tests_reports_missing_programs() {
+        let mut missing = definition("node", "cognia-missing-smoke-binary");
+        missing.approved_command = Some(missing.command_signature());
+        let custom = vec![missing];
+        let report = run_smoke_tests("zig", "0.12.0", &EnvModifications::new(), &custom)
+            .await
+            .unwrap();
+        assert!(report.passed);
+        assert!(report.results.is_empty());
+
+        let report = run_smoke_tests("node", "20.11.0", &EnvModifications::new(), &custom)
+            .await
+            .unwrap();
+        let missing = report.results.last().unwrap();
+        assert!(!missing.builtin);
+        assert!(!missing.passed);
+        assert!(missing
+            .message
+            .as_deref()
+            .unwrap()
+            .starts_with("failed to start"));
+        assert!(!report.passed);
+    }
+}
@@ -0,0 +1,507 @@
+//! Environment stacks: a shareable bundle of environment versions, global
+//! packages, a terminal profile template and environment variables.
+//!
+//! A stack is planned against a snapshot of the machine ([`MachineState`])
+//! so the preview and the apply path agree on what needs to change.
+
+use crate::core::profiles::ProfileEnvironment;
+use crate::core::terminal::TerminalProfileTemplate;
+use crate::error::{CogniaError, CogniaResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Current stack file format. Files with a newer version are rejected.
+pub const STACK_FORMAT_VERSION: u32 = 1;
+
+/// Providers whose global packages are captured when none are requested
+pub const DEFAULT_STACK_PACKAGE_PROVIDERS: &[&str] = &["npm", "pnpm", "pipx", "cargo", "gem"];
+
+/// A global package installed through a specific provider
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StackPackage {
+    pub provider: String,
+    pub name: String,
+    /// Exact version or prefix; `None` accepts any installed version
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+impl StackPackage {
+    /// Package spec in the `provider:name@version` form batch install takes
+    pub fn spec(&self) -> String {
+        match &self.version {
+            Some(version) => format!("{}:{}@{}", self.provider, self.name, version),
+            None => format!("{}:{}", self.provider, self.name),
+        }
+    }
+
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.provider, self.name)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Stack {
+    #[serde(default = "default_format_version")]
+    pub format_version: u32,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub environments: Vec<ProfileEnvironment>,
+    #[serde(default)]
+    pub packages: Vec<StackPackage>,
+    #[serde(default)]
+    pub terminal_profile: Option<TerminalProfileTemplate>,
+    /// Persistent user environment variables to export
+    #[serde(default)]
+    pub env_vars: BTreeMap<String, String>,
+    #[serde(default)]
+    pub created_at: String,
+}
+
+fn default_format_version() -> u32 {
+    STACK_FORMAT_VERSION
+}
+
+impl Stack {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            format_version: STACK_FORMAT_VERSION,
+            name: name.into(),
+            description: None,
+            environments: Vec::new(),
+            packages: Vec::new(),
+            terminal_profile: None,
+            env_vars: BTreeMap::new(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    pub fn from_json(json: &str) -> CogniaResult<Self> {
+        let stack: Self = serde_json::from_str(json)
+            .map_err(|e| CogniaError::Parse(format!("Invalid stack file: {}", e)))?;
+        if stack.format_version == 0 || stack.format_version > STACK_FORMAT_VERSION {
+            return Err(CogniaError::Parse(format!(
+                "Unsupported stack format version {} (this version supports up to {})",
+                stack.format_version, STACK_FORMAT_VERSION
+            )));
+        }
+        if stack.name.trim().is_empty() {
+            return Err(CogniaError::Parse("Stack name cannot be empty".into()));
+        }
+        Ok(stack)
+    }
+
+    pub fn to_json(&self) -> CogniaResult<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| CogniaError::Parse(format!("Failed to serialize stack: {}", e)))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StackItemKind {
+    Environment,
+    Package,
+    TerminalProfile,
+    EnvVar,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StackAction {
+    /// Install the version (environments then switch the global version to it)
+    Install,
+    /// Installed already, only the global version differs
+    SwitchGlobal,
+    Create,
+    Set,
+    /// The machine already matches
+    None,
+}
+
+/// One difference between a stack and the machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StackPlanItem {
+    pub kind: StackItemKind,
+    /// Env type, `provider:name`, profile name or variable name
+    pub key: String,
+    pub action: StackAction,
+    pub current: Option<String>,
+    pub target: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StackPlan {
+    pub stack_name: String,
+    pub items: Vec<StackPlanItem>,
+}
+
+impl StackPlan {
+    pub fn pending(&self) -> impl Iterator<Item = &StackPlanItem> {
+        self.items.iter().filter(|i| i.action != StackAction::None)
+    }
+
+    pub fn items_with(
+        &self,
+        kind: StackItemKind,
+        action: StackAction,
+    ) -> impl Iterator<Item = &StackPlanItem> {
+        self.items
+            .iter()
+            .filter(move |i| i.kind == kind && i.action == action)
+    }
+}
+
+/// The parts of the machine a stack can change
+#[derive(Debug, Clone, Default)]
+pub struct MachineState {
+    /// Installed versions per env type
+    pub installed_envs: HashMap<String, Vec<String>>,
+    /// Global version per env type
+    pub current_envs: HashMap<String, String>,
+    /// Installed version per `provider:name`
+    pub installed_packages: HashMap<String, String>,
+    pub terminal_profiles: HashSet<String>,
+    pub env_vars: HashMap<String, String>,
+}
+
+/// Whether `actual` satisfies the pinned `wanted` version. A pin of `20` or
+/// `3.12` accepts any release of that line.
+pub fn version_matches(wanted: &str, actual: &str) -> bool {
+    let wanted = wanted.trim().trim_start_matches('v');
+    let actual = actual.trim().trim_start_matches('v');
+    actual == wanted
+        || actual
+            .strip_prefix(wanted)
+            .is_some_and(|rest| rest.starts_with('.'))
+}
+
+fn version_key(version: &str) -> Vec<u64> {
+    version
+        .trim()
+        .trim_start_matches('v')
+        .split(['.', '-', '+'])
+        .map_while(|part| part.parse::<u64>().ok())
+        .collect()
+}
+
+/// Newest of `installed` that satisfies the pinned `wanted` version
+pub fn best_installed_match<'a>(wanted: &str, installed: &'a [String]) -> Option<&'a str> {
+    installed
+        .iter()
+        .filter(|v| version_matches(wanted, v))
+        .max_by_key(|v| version_key(v))
+        .map(String::as_str)
+}
+
+/// Diff `stack` against `machine`, one item per stack entry. Switching to an
+/// installed version targets the newest installed release of the pinned line.
+pub fn plan_stack(stack: &Stack, machine: &MachineState) -> StackPlan {
+    let mut items = Vec::new();
+
+    for env in &stack.environments {
+        let current = machine.current_envs.get(&env.env_type).cloned();
+        let installed = machine
+            .installed_envs
+            .get(&env.env_type)
+            .and_then(|versions| best_installed_match(&env.version, versions));
+        let (action, target) = if current
+            .as_deref()
+            .is_some_and(|c| version_matches(&env.version, c))
+        {
+            (StackAction::None, current.clone())
+        } else if let Some(version) = installed {
+            (StackAction::SwitchGlobal, Some(version.to_string()))
+        } else {
+            (StackAction::Install, Some(env.version.clone()))
+        };
+        items.push(StackPlanItem {
+            kind: StackItemKind::Environment,
+            key: env.env_type.clone(),
+            action,
+            current,
+            target,
+        });
+    }
+
+    for package in &stack.packages {
+        let current = machine.installed_packages.get(&package.key()).cloned();
+        let satisfied = match (&current, &package.version) {
+            (Some(installed), Some(wanted)) => version_matches(wanted, installed),
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        items.push(StackPlanItem {
+            kind: StackItemKind::Package,
+            key: package.key(),
+            action: if satisfied {
+                StackAction::None
+            } else {
+                StackAction::Install
+            },
+            current,
+            target: package.version.clone(),
+        });
+    }
+
+    if let Some(template) = &stack.terminal_profile {
+        let exists = machine.terminal_profiles.contains(&template.name);
+        items.push(StackPlanItem {
+            kind: StackItemKind::TerminalProfile,
+            key: template.name.clone(),
+            action: if exists {
+                StackAction::None
+            } else {
+                StackAction::Create
+            },
+            current: exists.then(|| template.name.clone()),
+            target: Some(template.name.clone()),
+        });
+    }
+
+    for (key, value) in &stack.env_vars {
+        let current = machine.env_vars.get(key).cloned();
+        items.push(StackPlanItem {
+            kind: StackItemKind::EnvVar,
+            key: key.clone(),
+            action: if current.as_deref() == Some(value.as_str()) {
+                StackAction::None
+            } else {
+                StackAction::Set
+            },
+            current,
+            target: Some(value.clone()),
+        });
+    }
+
+    StackPlan {
+        stack_name: stack.name.clone(),
+        items,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StackItemState {
+    Pending,
+    Completed,
+    Failed,
+    /// Nothing to do, or the step it depends on failed
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StackApplyItem {
+    pub kind: StackItemKind,
+    pub key: String,
+    pub action: StackAction,
+    pub state: StackItemState,
+    pub message: Option<String>,
+}
+
+/// Per-item outcome of applying a stack. One failing item never stops the rest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StackApplyResult {
+    pub stack_name: String,
+    pub items: Vec<StackApplyItem>,
+}
+
+impl StackApplyResult {
+    pub fn from_plan(plan: &StackPlan) -> Self {
+        let items = plan
+            .items
+            .iter()
+            .map(|item| StackApplyItem {
+                kind: item.kind,
+                key: item.key.clone(),
+                action: item.action,
+                state: if item.action == StackAction::None {
+                    StackItemState::Skipped
+                } else {
+                    StackItemState::Pending
+                },
+                message: None,
+            })
+            .collect();
+        Self {
+            stack_name: plan.stack_name.clone(),
+            items,
+        }
+    }
+
+    /// Record the outcome of a pending item. Returns false if there is none.
+    pub fn finish(
+        &mut self,
+        kind: StackItemKind,
+        key: &str,
+        state: StackItemState,
+        message: Option<String>,
+    ) -> bool {
+        let Some(item) = self
+            .items
+            .iter_mut()
+            .find(|i| i.kind == kind && i.key == key && i.state == StackItemState::Pending)
+        else {
+            return false;
+        };
+        item.state = state;
+        item.message = message;
+        true
+    }
+
+    pub fn count(&self, state: StackItemState) -> usize {
+        self.items.iter().filter(|i| i.state == state).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_stack() -> Stack {
+        let mut stack = Stack::new("onboarding");
+        stack.environments = vec![
+            ProfileEnvironment {
+                env_type: "node".into(),
+                version: "20".into(),
+                provider_id: Some("fnm".into()),
+            },
+            ProfileEnvironment {
+                env_type: "python".into(),
+                version: "3.12".into(),
+                provider_id: None,
+            },
+            ProfileEnvironment {
+                env_type: "rust".into(),
+                version: "stable".into(),
+                provider_id: None,
+            },
+        ];
+        stack.packages = vec![
+            StackPackage {
+                provider: "npm".into(),
+                name: "pnpm".into(),
+                version: None,
+            },
+            StackPackage {
+                provider: "pipx".into(),
+                name: "poetry".into(),
+                version: Some("1.8".into()),
+            },
+        ];
+        stack.env_vars = BTreeMap::from([("RUSTC_WRAPPER".into(), "sccache".into())]);
+        stack
+    }
+
+    #[test]
+    fn test_version_matches_prefix_lines() {
+        assert!(version_matches("20", "20.11.0"));
+        assert!(version_matches("v20.11", "20.11.0"));
+        assert!(version_matches("stable", "stable"));
+        assert!(!version_matches("2", "20.11.0"));
+        assert!(!version_matches("3.12", "3.1.2"));
+
+        let installed = vec!["20.9.0".to_string(), "20.11.0".into(), "18.19.0".into()];
+        assert_eq!(best_installed_match("20", &installed), Some("20.11.0"));
+        assert_eq!(best_installed_match("22", &installed), None);
+    }
+
+    #[test]
+    fn test_package_spec() {
+        let stack = sample_stack();
+        assert_eq!(stack.packages[0].spec(), "npm:pnpm");
+        assert_eq!(stack.packages[1].spec(), "pipx:poetry@1.8");
+    }
+
+    #[test]
+    fn test_plan_against_machine() {
+        let machine = MachineState {
+            installed_envs: HashMap::from([
+                (
+                    "node".into(),
+                    vec!["18.19.0".into(), "20.11.0".into(), "20.9.0".into()],
+                ),
+                ("python".into(), vec!["3.12.1".into()]),
+            ]),
+            current_envs: HashMap::from([
+                ("node".into(), "18.19.0".into()),
+                ("python".into(), "3.12.1".into()),
+            ]),
+            installed_packages: HashMap::from([("pipx:poetry".into(), "1.7.1".into())]),
+            terminal_profiles: HashSet::new(),
+            env_vars: HashMap::from([("RUSTC_WRAPPER".into(), "sccache".into())]),
+        };
+
+        let plan = plan_stack(&sample_stack(), &machine);
+        let actions: Vec<(&str, StackAction)> = plan
+            .items
+            .iter()
+            .map(|i| (i.key.as_str(), i.action))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                ("node", StackAction::SwitchGlobal),
+                ("python", StackAction::None),
+                ("rust", StackAction::Install),
+                ("npm:pnpm", StackAction::Install),
+                ("pipx:poetry", StackAction::Install),
+                ("RUSTC_WRAPPER", StackAction::None),
+            ]
+        );
+        assert_eq!(plan.pending().count(), 4);
+        assert_eq!(plan.items[0].target.as_deref(), Some("20.11.0"));
+        assert_eq!(plan.items[4].current.as_deref(), Some("1.7.1"));
+    }
+
+    #[test]
+    fn test_apply_result_isolates_items() {
+        let plan = plan_stack(&sample_stack(), &MachineState::default());
+        let mut result = StackApplyResult::from_plan(&plan);
+        assert_eq!(result.count(StackItemState::Pending), plan.items.len());
+
+        assert!(result.finish(
+            StackItemKind::Package,
+            "npm:pnpm",
+            StackItemState::Failed,
+            Some("network".into())
+        ));
+        assert!(result.finish(
+            StackItemKind::Environment,
+            "node",
+            StackItemState::Completed,
+            None
+        ));
+        assert!(!result.finish(
+            StackItemKind::Environment,
+            "node",
+            StackItemState::Failed,
+            None
+        ));
+        assert_eq!(result.count(StackItemState::Failed), 1);
+        assert_eq!(result.count(StackItemState::Completed), 1);
+        assert_eq!(result.count(StackItemState::Pending), plan.items.len() - 2);
+    }
+
+    #[test]
+    fn test_json_round_trip_and_version_check() {
+        let stack = sample_stack();
+        let parsed = Stack::from_json(&stack.to_json().unwrap()).unwrap();
+        assert_eq!(parsed.name, "onboarding");
+        assert_eq!(parsed.packages, stack.packages);
+        assert_eq!(parsed.env_vars, stack.env_vars);
+
+        let minimal = Stack::from_json(r#"{"name": "bare"}"#).unwrap();
+        assert_eq!(minimal.format_version, STACK_FORMAT_VERSION);
+        assert!(minimal.environments.is_empty());
+
+        let newer = format!(
+            r#"{{"format_version": {}, "name": "x"}}"#,
+            STACK_FORMAT_VERSION + 1
+        );
+        assert!(Stack::from_json(&newer).is_err());
+        assert!(Stack::from_json(r#"{"name": "  "}"#).is_err());
+    }
+}
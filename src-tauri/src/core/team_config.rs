@@ -0,0 +1,465 @@
+//! Team config bundles: a signed subset of the settings (mirrors, proxy,
+//! provider enablement) plus custom detection rules, exported once and
+//! applied on every team member's machine.
+//!
+//! The bundle is signed with HMAC-SHA256 under a key derived from a shared
+//! passphrase. After a bundle is applied, per-section hashes of the local
+//! values are kept in `team_config.json` so drift can be reported later.
+
+use crate::config::{ConfigChange, ConfigChangeKind, Settings, SettingsLoadReport};
+use crate::core::custom_detection::CustomDetectionRule;
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::fs;
+use ring::hmac;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::num::NonZeroU32;
+use std::path::Path;
+
+/// Layout version of bundles written by this release
+pub const TEAM_CONFIG_BUNDLE_VERSION: u32 = 1;
+const STATE_FILE: &str = "team_config.json";
+const SALT_LEN: usize = 16;
+const PBKDF2_ITERATIONS: u32 = 100_000;
+/// Key prefix of detection rule entries in a [`TeamConfigPreview`]
+pub const RULE_KEY_PREFIX: &str = "detection_rules.";
+
+/// Part of the configuration a bundle can carry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TeamConfigSection {
+    /// `mirrors.*`
+    Mirrors,
+    /// `network.proxy`, `network.no_proxy` and per-provider proxies
+    Proxy,
+    /// Provider enablement and priority, and the disabled provider list
+    Providers,
+    /// Custom detection rules
+    DetectionRules,
+}
+
+impl TeamConfigSection {
+    /// Whether the dotted settings key belongs to this section
+    pub fn owns_key(self, key: &str) -> bool {
+        match self {
+            Self::Mirrors => key.starts_with("mirrors."),
+            Self::Proxy => {
+                matches!(key, "network.proxy" | "network.no_proxy")
+                    || key.starts_with("network.provider_proxies.")
+            }
+            Self::Providers => {
+                key == "provider_settings.disabled_providers"
+                    || key
+                        .strip_prefix("providers.")
+                        .and_then(|rest| rest.split_once('.'))
+                        .is_some_and(|(_, field)| matches!(field, "enabled" | "priority"))
+            }
+            Self::DetectionRules => false,
+        }
+    }
+}
+
+/// Signed bundle as exported
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamConfigBundle {
+    pub version: u32,
+    /// JSON-encoded [`TeamConfigPayload`], signed byte for byte
+    pub payload: String,
+    /// Hex PBKDF2 salt the signing key was derived with
+    pub salt: String,
+    /// Hex HMAC-SHA256 of `payload`
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamConfigPayload {
+    /// Repeated from the bundle so the version is covered by the signature
+    pub version: u32,
+    pub created_at: String,
+    pub sections: Vec<TeamConfigSection>,
+    /// Settings keys of the included sections, as a TOML document
+    pub settings: String,
+    #[serde(default)]
+    pub detection_rules: Vec<CustomDetectionRule>,
+}
+
+/// What applying a bundle changes (or changed)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamConfigPreview {
+    pub version: u32,
+    pub created_at: String,
+    pub sections: Vec<TeamConfigSection>,
+    /// Settings keys and `detection_rules.<id>` entries that differ
+    pub changes: Vec<ConfigChange>,
+    /// Migration and validation notes for the bundled settings
+    pub report: SettingsLoadReport,
+}
+
+/// Record of the last applied bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamConfigState {
+    pub version: u32,
+    pub created_at: String,
+    pub applied_at: String,
+    /// SHA-256 of the bundle payload
+    pub bundle_hash: String,
+    /// Hash of the local values of each section right after applying
+    pub section_hashes: BTreeMap<TeamConfigSection, String>,
+    /// Rules the bundle carried, the only ones checked for drift
+    #[serde(default)]
+    pub rule_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamConfigStatus {
+    /// Whether a bundle was ever applied on this machine
+    pub applied: bool,
+    pub version: Option<u32>,
+    pub created_at: Option<String>,
+    pub applied_at: Option<String>,
+    pub bundle_hash: Option<String>,
+    /// Local settings no longer match the last applied bundle
+    pub drifted: bool,
+    pub drifted_sections: Vec<TeamConfigSection>,
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+fn signing_key(passphrase: &str, salt: &[u8]) -> CogniaResult<hmac::Key> {
+    if passphrase.is_empty() {
+        return Err(CogniaError::Config(
+            "A passphrase is required to sign or verify a team config bundle".into(),
+        ));
+    }
+    let iterations = NonZeroU32::new(PBKDF2_ITERATIONS).expect("iterations are non-zero");
+    let mut key = [0u8; 32];
+    ring::pbkdf2::derive(
+        ring::pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    Ok(hmac::Key::new(hmac::HMAC_SHA256, &key))
+}
+
+fn normalized_sections(sections: &[TeamConfigSection]) -> Vec<TeamConfigSection> {
+    let mut sections = sections.to_vec();
+    sections.sort();
+    sections.dedup();
+    sections
+}
+
+fn key_filter(sections: &[TeamConfigSection]) -> impl Fn(&str) -> bool + '_ {
+    move |key| sections.iter().any(|section| section.owns_key(key))
+}
+
+/// A rule without the fields that legitimately differ between machines
+fn shareable_rule(rule: &CustomDetectionRule) -> CustomDetectionRule {
+    let mut rule = rule.clone();
+    rule.approved_command = None;
+    rule.created_at = None;
+    rule.updated_at = None;
+    rule
+}
+
+/// Collect the chosen sections of the local configuration
+pub fn build_payload(
+    settings: &Settings,
+    rules: &[CustomDetectionRule],
+    sections: &[TeamConfigSection],
+) -> CogniaResult<TeamConfigPayload> {
+    let sections = normalized_sections(sections);
+    if sections.is_empty() {
+        return Err(CogniaError::Config(
+            "Select at least one section to export".into(),
+        ));
+    }
+    let fragment = settings.select_keys(key_filter(&sections))?;
+    let settings = toml::to_string(&fragment)
+        .map_err(|e| CogniaError::Config(format!("Failed to serialize config: {}", e)))?;
+    let detection_rules = if sections.contains(&TeamConfigSection::DetectionRules) {
+        rules.iter().map(shareable_rule).collect()
+    } else {
+        Vec::new()
+    };
+    Ok(TeamConfigPayload {
+        version: TEAM_CONFIG_BUNDLE_VERSION,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        sections,
+        settings,
+        detection_rules,
+    })
+}
+
+/// Sign `payload` with a key derived from `passphrase`
+pub fn seal(payload: &TeamConfigPayload, passphrase: &str) -> CogniaResult<TeamConfigBundle> {
+    let mut salt = [0u8; SALT_LEN];
+    SystemRandom::new()
+        .fill(&mut salt)
+        .map_err(|_| CogniaError::Internal("Failed to generate a bundle salt".into()))?;
+    let key = signing_key(passphrase, &salt)?;
+    let payload = serde_json::to_string(payload)
+        .map_err(|e| CogniaError::Internal(format!("Failed to serialize bundle: {}", e)))?;
+    let signature = hmac::sign(&key, payload.as_bytes());
+    Ok(TeamConfigBundle {
+        version: TEAM_CONFIG_BUNDLE_VERSION,
+        signature: hex::encode(signature.as_ref()),
+        salt: hex::encode(salt),
+        payload,
+    })
+}
+
+/// Parse a bundle and verify its signature. Returns the payload and the
+/// hash identifying the bundle.
+pub fn open(bundle: &str, passphrase: &str) -> CogniaResult<(TeamConfigPayload, String)> {
+    let bundle: TeamConfigBundle = serde_json::from_str(bundle)
+        .map_err(|e| CogniaError::Parse(format!("Invalid team config bundle: {}", e)))?;
+    if bundle.version > TEAM_CONFIG_BUNDLE_VERSION {
+        return Err(CogniaError::Unsupported(format!(
+            "Team config bundle version {} is newer than the supported version {}",
+            bundle.version, TEAM_CONFIG_BUNDLE_VERSION
+        )));
+    }
+    let salt = hex::decode(&bundle.salt)
+        .map_err(|_| CogniaError::Parse("Invalid team config bundle salt".into()))?;
+    let signature = hex::decode(&bundle.signature)
+        .map_err(|_| CogniaError::Parse("Invalid team config bundle signature".into()))?;
+    let key = signing_key(passphrase, &salt)?;
+    hmac::verify(&key, bundle.payload.as_bytes(), &signature).map_err(|_| {
+        CogniaError::PermissionDenied(
+            "Team config bundle signature does not match; wrong passphrase or modified bundle"
+                .into(),
+        )
+    })?;
+    let payload: TeamConfigPayload = serde_json::from_str(&bundle.payload)
+        .map_err(|e| CogniaError::Parse(format!("Invalid team config bundle: {}", e)))?;
+    if payload.version != bundle.version {
+        return Err(CogniaError::Parse(
+            "Team config bundle version does not match its payload".into(),
+        ));
+    }
+    Ok((payload, sha256_hex(bundle.payload.as_bytes())))
+}
+
+/// The current settings with the bundled sections swapped in
+pub fn incoming_settings(
+    current: &Settings,
+    payload: &TeamConfigPayload,
+) -> CogniaResult<(Settings, SettingsLoadReport)> {
+    let fragment: toml::Table = toml::from_str(&payload.settings)
+        .map_err(|e| CogniaError::Parse(format!("Invalid bundled settings: {}", e)))?;
+    current.replace_keys(&fragment, key_filter(&payload.sections))
+}
+
+/// Bundled rules that would be added or changed, as config changes keyed
+/// `detection_rules.<id>`. Local rules missing from the bundle are kept.
+pub fn rule_changes(
+    current: &[CustomDetectionRule],
+    payload: &TeamConfigPayload,
+) -> Vec<ConfigChange> {
+    payload
+        .detection_rules
+        .iter()
+        .filter_map(|incoming| {
+            let existing = current.iter().find(|rule| rule.id == incoming.id);
+            let kind = match existing {
+                None => ConfigChangeKind::Added,
+                Some(rule) if rule_hash(Some(rule)) != rule_hash(Some(incoming)) => {
+                    ConfigChangeKind::Changed
+                }
+                Some(_) => return None,
+            };
+            Some(ConfigChange {
+                key: format!("{}{}", RULE_KEY_PREFIX, incoming.id),
+                kind,
+                old_value: existing.map(|rule| rule.name.clone()),
+                new_value: Some(incoming.name.clone()),
+                secret: false,
+                requires_reinit: false,
+            })
+        })
+        .collect()
+}
+
+fn rule_hash(rule: Option<&CustomDetectionRule>) -> String {
+    let encoded = rule
+        .map(|rule| serde_json::to_string(&shareable_rule(rule)).unwrap_or_default())
+        .unwrap_or_default();
+    sha256_hex(encoded.as_bytes())
+}
+
+fn section_hash(
+    section: TeamConfigSection,
+    settings: &Settings,
+    rules: &[CustomDetectionRule],
+    rule_ids: &[String],
+) -> CogniaResult<String> {
+    if section == TeamConfigSection::DetectionRules {
+        let hashes: Vec<String> = rule_ids
+            .iter()
+            .map(|id| rule_hash(rules.iter().find(|rule| &rule.id == id)))
+            .collect();
+        return Ok(sha256_hex(hashes.join("\n").as_bytes()));
+    }
+    let fragment = settings.select_keys(|key| section.owns_key(key))?;
+    let encoded = toml::to_string(&fragment)
+        .map_err(|e| CogniaError::Config(format!("Failed to serialize config: {}", e)))?;
+    Ok(sha256_hex(encoded.as_bytes()))
+}
+
+/// Record the local state right after `payload` was applied
+pub fn applied_state(
+    payload: &TeamConfigPayload,
+    bundle_hash: String,
+    settings: &Settings,
+    rules: &[CustomDetectionRule],
+) -> CogniaResult<TeamConfigState> {
+    let rule_ids: Vec<String> = payload
+        .detection_rules
+        .iter()
+        .map(|rule| rule.id.clone())
+        .collect();
+    let section_hashes = payload
+        .sections
+        .iter()
+        .map(|&section| Ok((section, section_hash(section, settings, rules, &rule_ids)?)))
+        .collect::<CogniaResult<_>>()?;
+    Ok(TeamConfigState {
+        version: payload.version,
+        created_at: payload.created_at.clone(),
+        applied_at: chrono::Utc::now().to_rfc3339(),
+        bundle_hash,
+        section_hashes,
+        rule_ids,
+    })
+}
+
+/// Compare the local configuration with the last applied bundle
+pub fn status(
+    state: Option<&TeamConfigState>,
+    settings: &Settings,
+    rules: &[CustomDetectionRule],
+) -> CogniaResult<TeamConfigStatus> {
+    let Some(state) = state else {
+        return Ok(TeamConfigStatus {
+            applied: false,
+            version: None,
+            created_at: None,
+            applied_at: None,
+            bundle_hash: None,
+            drifted: false,
+            drifted_sections: Vec::new(),
+        });
+    };
+    let mut drifted_sections = Vec::new();
+    for (&section, hash) in &state.section_hashes {
+        if &section_hash(section, settings, rules, &state.rule_ids)? != hash {
+            drifted_sections.push(section);
+        }
+    }
+    Ok(TeamConfigStatus {
+        applied: true,
+        version: Some(state.version),
+        created_at: Some(state.created_at.clone()),
+        applied_at: Some(state.applied_at.clone()),
+        bundle_hash: Some(state.bundle_hash.clone()),
+        drifted: !drifted_sections.is_empty(),
+        drifted_sections,
+    })
+}
+
+pub async fn load_state(state_dir: &Path) -> CogniaResult<Option<TeamConfigState>> {
+    let loaded = fs::read_json_with_backup(&state_dir.join(STATE_FILE))
+        .await
+        .map_err(|e| CogniaError::Config(format!("Failed to parse team config state: {}", e)))?;
+    Ok(loaded.into_value("Team config state"))
+}
+
+pub async fn save_state(state_dir: &Path, state: &TeamConfigState) -> CogniaResult<()> {
+    fs::create_dir_all(state_dir).await?;
+    fs::atomic_write_json(&state_dir.join(STATE_FILE), state, true).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MirrorConfig;
+
+    fn team_settings() -> Settings {
+        let mut settings = Settings::default();
+        settings.mirrors.insert(
+            "npm".into(),
+            MirrorConfig::new("https://registry.npmmirror.com"),
+        );
+        settings.network.proxy = Some("http://proxy.corp:3128".into());
+        settings.general.parallel_downloads = 8;
+        settings
+    }
+
+    #[test]
+    fn test_section_key_ownership() {
+        assert!(TeamConfigSection::Mirrors.owns_key("mirrors.npm.url"));
+        assert!(TeamConfigSection::Proxy.owns_key("network.proxy"));
+        assert!(TeamConfigSection::Proxy.owns_key("network.provider_proxies.github"));
+        assert!(!TeamConfigSection::Proxy.owns_key("network.timeout"));
+        assert!(TeamConfigSection::Providers.owns_key("providers.brew.enabled"));
+        assert!(TeamConfigSection::Providers.owns_key("provider_settings.disabled_providers"));
+        assert!(!TeamConfigSection::Providers.owns_key("providers.github.token"));
+    }
+
+    #[test]
+    fn test_bundle_round_trip_and_tampering() {
+        let payload = build_payload(
+            &team_settings(),
+            &[],
+            &[TeamConfigSection::Proxy, TeamConfigSection::Mirrors],
+        )
+        .unwrap();
+        assert!(!payload.settings.contains("parallel_downloads"));
+        let bundle = serde_json::to_string(&seal(&payload, "team secret").unwrap()).unwrap();
+
+        let (opened, hash) = open(&bundle, "team secret").unwrap();
+        assert_eq!(
+            opened.sections,
+            vec![TeamConfigSection::Mirrors, TeamConfigSection::Proxy]
+        );
+        assert_eq!(hash.len(), 64);
+        assert!(open(&bundle, "wrong").is_err());
+
+        let tampered = bundle.replace("proxy.corp", "evil.example");
+        assert!(matches!(
+            open(&tampered, "team secret"),
+            Err(CogniaError::PermissionDenied(_))
+        ));
+    }
+
+    #[test]
+    fn test_apply_preview_and_drift() {
+        let payload = build_payload(&team_settings(), &[], &[TeamConfigSection::Mirrors]).unwrap();
+        let current = Settings::default();
+        let (incoming, _) = incoming_settings(&current, &payload).unwrap();
+        let changes = current.diff(&incoming).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].key, "mirrors.npm.url");
+        assert_eq!(incoming.general.parallel_downloads, 4);
+
+        let state = applied_state(&payload, "hash".into(), &incoming, &[]).unwrap();
+        assert!(!status(Some(&state), &incoming, &[]).unwrap().drifted);
+
+        let mut edited = incoming.clone();
+        edited.general.parallel_downloads = 2;
+        assert!(!status(Some(&state), &edited, &[]).unwrap().drifted);
+        edited.mirrors.clear();
+        let report = status(Some(&state), &edited, &[]).unwrap();
+        assert!(report.drifted);
+        assert_eq!(report.drifted_sections, vec![TeamConfigSection::Mirrors]);
+    }
+}
@@ -3,6 +3,7 @@
 use crate::platform::{fs, process};
 use chrono::Utc;
 use log::debug;
+use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -698,24 +699,18 @@ pub async fn new(base_dir: &Path) -> CogniaResult<Self> {
         let profiles_path = profiles_dir.join("profiles.json");
         let templates_path = profiles_dir.join("templates.json");
 
-        let profiles = if fs::exists(&profiles_path).await {
-            let content = fs::read_file_string(&profiles_path)
-                .await
-                .map_err(|e| CogniaError::Internal(format!("Failed to read profiles: {}", e)))?;
-            serde_json::from_str(&content).map_err(|e| {
-                CogniaError::Config(format!("Failed to parse terminal profiles: {}", e))
-            })?
-        } else {
-            Vec::new()
-        };
-
-        let custom_templates = if fs::exists(&templates_path).await {
-            let content = fs::read_file_string(&templates_path)
-                .await
-                .map_err(|e| CogniaError::Internal(format!("Failed to read templates: {}", e)))?;
-            serde_json::from_str(&content).unwrap_or_default()
-        } else {
-            Vec::new()
+        let profiles = fs::read_json_with_backup(&profiles_path)
+            .await
+            .map_err(|e| CogniaError::Config(format!("Failed to parse terminal profiles: {}", e)))?
+            .into_value("Terminal profiles")
+            .unwrap_or_default();
+
+        let custom_templates = match fs::read_json_with_backup(&templates_path).await {
+            Ok(loaded) => loaded.into_value("Terminal templates").unwrap_or_default(),
+            Err(e) => {
+                log::warn!("Failed to read terminal templates, starting empty: {}", e);
+                Vec::new()
+            }
         };
 
         Ok(Self {
@@ -727,10 +722,7 @@ pub async fn new(base_dir: &Path) -> CogniaResult<Self> {
     }
 
     async fn save(&self) -> CogniaResult<()> {
-        let content = serde_json::to_string_pretty(&self.profiles).map_err(|e| {
-            CogniaError::Config(format!("Failed to serialize terminal profiles: {}", e))
-        })?;
-        fs::write_file_string(&self.profiles_path, &content)
+        fs::atomic_write_json(&self.profiles_path, &self.profiles, true)
             .await
             .map_err(|e| CogniaError::Internal(format!("Failed to save profiles: {}", e)))?;
         Ok(())
@@ -793,6 +785,25 @@ pub async fn delete_profile(&mut self, id: &str) -> CogniaResult<bool> {
         Ok(deleted)
     }
 
+    /// Re-add a profile from the trash, keeping its ID and timestamps. It only
+    /// stays the default if no other profile became default meanwhile.
+    pub async fn restore_profile(&mut self, mut profile: TerminalProfile) -> CogniaResult<String> {
+        if self.get_profile(&profile.id).is_some() {
+            return Err(CogniaError::Config(format!(
+                "Profile '{}' already exists",
+                profile.id
+            )));
+        }
+        if profile.is_default && self.get_default_profile().is_some() {
+            profile.is_default = false;
+        }
+
+        let id = profile.id.clone();
+        self.profiles.push(profile);
+        self.save().await?;
+        Ok(id)
+    }
+
     pub fn get_default_profile(&self) -> Option<&TerminalProfile> {
         self.profiles.iter().find(|p| p.is_default)
     }
@@ -882,6 +893,65 @@ pub struct TerminalProfileTemplate {
     pub is_builtin: bool,
 }
 
+/// Custom template carrying a profile's shell, arguments and environment
+pub fn template_from_profile(
+    profile: TerminalProfile,
+    name: String,
+    description: String,
+) -> TerminalProfileTemplate {
+    TerminalProfileTemplate {
+        id: String::new(),
+        name,
+        description,
+        icon: "user".into(),
+        category: TemplateCategory::Custom,
+        shell_type: ShellType::from_id(&profile.shell_id),
+        args: profile.args,
+        env_vars: profile.env_vars,
+        cwd: profile.cwd,
+        startup_command: profile.startup_command,
+        env_type: profile.env_type,
+        env_version: profile.env_version,
+        is_builtin: false,
+    }
+}
+
+/// Unsaved profile built from `template`, using the detected shell of the
+/// template's type or the first detected shell
+pub fn profile_from_template(
+    template: &TerminalProfileTemplate,
+    detected_shells: &[ShellInfo],
+) -> TerminalProfile {
+    let shell_id = if let Some(ref st) = template.shell_type {
+        detected_shells
+            .iter()
+            .find(|s| &s.shell_type == st)
+            .map(|s| s.id.clone())
+            .unwrap_or_else(|| st.to_id().to_string())
+    } else {
+        detected_shells
+            .first()
+            .map(|s| s.id.clone())
+            .unwrap_or_default()
+    };
+
+    TerminalProfile {
+        id: String::new(),
+        name: template.name.clone(),
+        shell_id,
+        args: template.args.clone(),
+        env_vars: template.env_vars.clone(),
+        cwd: template.cwd.clone(),
+        startup_command: template.startup_command.clone(),
+        env_type: template.env_type.clone(),
+        env_version: template.env_version.clone(),
+        color: None,
+        is_default: false,
+        created_at: String::new(),
+        updated_at: String::new(),
+    }
+}
+
 pub fn get_builtin_templates() -> Vec<TerminalProfileTemplate> {
     vec![
         TerminalProfileTemplate {
@@ -1083,22 +1153,7 @@ pub async fn save_profile_as_template(
             .cloned()
             .ok_or_else(|| CogniaError::Config(format!("Profile '{}' not found", profile_id)))?;
 
-        let template = TerminalProfileTemplate {
-            id: String::new(),
-            name: template_name,
-            description: template_description,
-            icon: "user".into(),
-            category: TemplateCategory::Custom,
-            shell_type: ShellType::from_id(&profile.shell_id),
-            args: profile.args,
-            env_vars: profile.env_vars,
-            cwd: profile.cwd,
-            startup_command: profile.startup_command,
-            env_type: profile.env_type,
-            env_version: profile.env_version,
-            is_builtin: false,
-        };
-
+        let template = template_from_profile(profile, template_name, template_description);
         self.create_custom_template(template).await
     }
 
@@ -1113,40 +1168,11 @@ pub fn create_profile_from_template(
             .find(|t| t.id == template_id)
             .ok_or_else(|| CogniaError::Config(format!("Template '{}' not found", template_id)))?;
 
-        let shell_id = if let Some(ref st) = template.shell_type {
-            detected_shells
-                .iter()
-                .find(|s| &s.shell_type == st)
-                .map(|s| s.id.clone())
-                .unwrap_or_else(|| st.to_id().to_string())
-        } else {
-            detected_shells
-                .first()
-                .map(|s| s.id.clone())
-                .unwrap_or_default()
-        };
-
-        Ok(TerminalProfile {
-            id: String::new(),
-            name: template.name.clone(),
-            shell_id,
-            args: template.args.clone(),
-            env_vars: template.env_vars.clone(),
-            cwd: template.cwd.clone(),
-            startup_command: template.startup_command.clone(),
-            env_type: template.env_type.clone(),
-            env_version: template.env_version.clone(),
-            color: None,
-            is_default: false,
-            created_at: String::new(),
-            updated_at: String::new(),
-        })
+        Ok(profile_from_template(template, detected_shells))
     }
 
     async fn save_templates(&self) -> CogniaResult<()> {
-        let content = serde_json::to_string_pretty(&self.custom_templates)
-            .map_err(|e| CogniaError::Config(format!("Failed to serialize templates: {}", e)))?;
-        fs::write_file_string(&self.templates_path, &content)
+        fs::atomic_write_json(&self.templates_path, &self.custom_templates, true)
             .await
             .map_err(|e| CogniaError::Internal(format!("Failed to save templates: {}", e)))?;
         Ok(())
@@ -1171,6 +1197,25 @@ pub enum TerminalConfigMutationOperation {
     Backup,
     Append,
     Write,
+    Upsert,
+    Remove,
+}
+
+/// Kind of definition edited by the structured config writers.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ShellConfigEntryKind {
+    Alias,
+    Export,
+}
+
+impl ShellConfigEntryKind {
+    fn label(self) -> &'static str {
+        match self {
+            ShellConfigEntryKind::Alias => "alias",
+            ShellConfigEntryKind::Export => "export",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -1852,16 +1897,8 @@ fn parse_posix_config(content: &str) -> ShellConfigEntries {
     let mut exports = Vec::new();
     let mut sources = Vec::new();
 
-    // alias name='value' — single-quoted value (greedy inside quotes)
-    let alias_sq = Regex::new(r#"^\s*alias\s+([A-Za-z_][\w-]*)='([^']*)'"#).unwrap();
-    // alias name="value" — double-quoted value (greedy inside quotes)
-    let alias_dq = Regex::new(r#"^\s*alias\s+([A-Za-z_][\w-]*)="([^"]*)""#).unwrap();
-    // alias name=value — unquoted value
-    let alias_uq = Regex::new(r#"^\s*alias\s+([A-Za-z_][\w-]*)=(\S+)\s*$"#).unwrap();
-    // export KEY=VALUE or export KEY="VALUE" or export KEY='VALUE'
-    let export_sq = Regex::new(r#"^\s*export\s+([A-Za-z_]\w*)='([^']*)'"#).unwrap();
-    let export_dq = Regex::new(r#"^\s*export\s+([A-Za-z_]\w*)="([^"]*)""#).unwrap();
-    let export_uq = Regex::new(r#"^\s*export\s+([A-Za-z_]\w*)=(\S+)\s*$"#).unwrap();
+    let alias_res = shell_entry_patterns(ShellType::Bash, ShellConfigEntryKind::Alias);
+    let export_res = shell_entry_patterns(ShellType::Bash, ShellConfigEntryKind::Export);
     // source file or . file (quoted paths may contain spaces)
     let source_re = Regex::new(r#"^\s*(?:source|\.) +(?:"([^"]+)"|'([^']+)'|(\S+))"#).unwrap();
 
@@ -1870,18 +1907,13 @@ fn parse_posix_config(content: &str) -> ShellConfigEntries {
         if line.is_empty() || line.starts_with('#') {
             continue;
         }
-        if let Some(caps) = alias_sq.captures(line) {
-            aliases.push((caps[1].to_string(), caps[2].to_string()));
-        } else if let Some(caps) = alias_dq.captures(line) {
+        if let Some(caps) = alias_res.iter().find_map(|re| re.captures(line)) {
             aliases.push((caps[1].to_string(), caps[2].to_string()));
-        } else if let Some(caps) = alias_uq.captures(line) {
-            aliases.push((caps[1].to_string(), caps[2].to_string()));
-        } else if let Some(caps) = export_sq.captures(line) {
-            exports.push((caps[1].to_string(), caps[2].to_string()));
-        } else if let Some(caps) = export_dq.captures(line) {
-            exports.push((caps[1].to_string(), caps[2].to_string()));
-        } else if let Some(caps) = export_uq.captures(line) {
-            exports.push((caps[1].to_string(), caps[2].to_string()));
+        } else if let Some(caps) = export_res.iter().find_map(|re| re.captures(line)) {
+            exports.push((
+                caps[1].to_string(),
+                decode_export_value(ShellType::Bash, &caps[2]),
+            ));
         } else if let Some(caps) = source_re.captures(line) {
             let path = caps
                 .get(1)
@@ -1905,11 +1937,8 @@ fn parse_fish_config(content: &str) -> ShellConfigEntries {
     let mut exports = Vec::new();
     let mut sources = Vec::new();
 
-    // alias name 'value' or alias name "value" or alias name=value
-    let alias_re = Regex::new(r#"^\s*alias\s+(\w+)\s+["']?(.*?)["']?\s*$"#).unwrap();
-    let alias_eq_re = Regex::new(r#"^\s*alias\s+(\w+)=["']?(.*?)["']?\s*$"#).unwrap();
-    // set -gx KEY VALUE
-    let export_re = Regex::new(r#"^\s*set\s+-[gx]+\s+(\w+)\s+["']?(.*?)["']?\s*$"#).unwrap();
+    let alias_res = shell_entry_patterns(ShellType::Fish, ShellConfigEntryKind::Alias);
+    let export_res = shell_entry_patterns(ShellType::Fish, ShellConfigEntryKind::Export);
     // source file (quoted paths may contain spaces)
     let source_re = Regex::new(r#"^\s*source\s+(?:"([^"]+)"|'([^']+)'|(\S+))"#).unwrap();
 
@@ -1918,12 +1947,13 @@ fn parse_fish_config(content: &str) -> ShellConfigEntries {
         if line.is_empty() || line.starts_with('#') {
             continue;
         }
-        if let Some(caps) = alias_re.captures(line) {
-            aliases.push((caps[1].to_string(), caps[2].to_string()));
-        } else if let Some(caps) = alias_eq_re.captures(line) {
+        if let Some(caps) = alias_res.iter().find_map(|re| re.captures(line)) {
             aliases.push((caps[1].to_string(), caps[2].to_string()));
-        } else if let Some(caps) = export_re.captures(line) {
-            exports.push((caps[1].to_string(), caps[2].to_string()));
+        } else if let Some(caps) = export_res.iter().find_map(|re| re.captures(line)) {
+            exports.push((
+                caps[1].to_string(),
+                decode_export_value(ShellType::Fish, &caps[2]),
+            ));
         } else if let Some(caps) = source_re.captures(line) {
             let path = caps
                 .get(1)
@@ -1947,13 +1977,8 @@ fn parse_powershell_config(content: &str) -> ShellConfigEntries {
     let mut exports = Vec::new();
     let mut sources = Vec::new();
 
-    // Set-Alias / New-Alias / sal / nal -Name name -Value value
-    let alias_re = Regex::new(
-        r#"(?i)^\s*(?:Set-Alias|New-Alias|sal|nal)\s+(?:-Name\s+)?(\w+)\s+(?:-Value\s+)?["']?([^"'\s]+)["']?"#,
-    )
-    .unwrap();
-    // $env:KEY = "VALUE"
-    let export_re = Regex::new(r#"^\s*\$env:(\w+)\s*=\s*["']?(.*?)["']?\s*$"#).unwrap();
+    let alias_res = shell_entry_patterns(ShellType::PowerShell, ShellConfigEntryKind::Alias);
+    let export_res = shell_entry_patterns(ShellType::PowerShell, ShellConfigEntryKind::Export);
     // . path or . "path" (dot-sourcing, quoted paths may contain spaces)
     let source_re = Regex::new(r#"^\s*\.\s+(?:"([^"]+)"|'([^']+)'|(\S+))"#).unwrap();
     // Import-Module name or Import-Module "name"
@@ -1964,10 +1989,13 @@ fn parse_powershell_config(content: &str) -> ShellConfigEntries {
         if line.is_empty() || line.starts_with('#') {
             continue;
         }
-        if let Some(caps) = alias_re.captures(line) {
+        if let Some(caps) = alias_res.iter().find_map(|re| re.captures(line)) {
             aliases.push((caps[1].to_string(), caps[2].to_string()));
-        } else if let Some(caps) = export_re.captures(line) {
-            exports.push((caps[1].to_string(), caps[2].to_string()));
+        } else if let Some(caps) = export_res.iter().find_map(|re| re.captures(line)) {
+            exports.push((
+                caps[1].to_string(),
+                decode_export_value(ShellType::PowerShell, &caps[2]),
+            ));
         } else if let Some(caps) = source_re.captures(line) {
             let path = caps
                 .get(1)
@@ -1999,10 +2027,8 @@ fn parse_nushell_config(content: &str) -> ShellConfigEntries {
     let mut exports = Vec::new();
     let mut sources = Vec::new();
 
-    // alias name = value or alias name = { body }
-    let alias_re = Regex::new(r#"^\s*alias\s+([\w-]+)\s*=\s*(.+)"#).unwrap();
-    // $env.KEY = "value" or $env.KEY = value
-    let export_re = Regex::new(r#"^\s*\$env\.(\w+)\s*=\s*["']?(.*?)["']?\s*$"#).unwrap();
+    let alias_res = shell_entry_patterns(ShellType::Nushell, ShellConfigEntryKind::Alias);
+    let export_res = shell_entry_patterns(ShellType::Nushell, ShellConfigEntryKind::Export);
     // source path or source-env path or use path (quoted paths may contain spaces)
     let source_re =
         Regex::new(r#"^\s*(?:source|source-env|use)\s+(?:"([^"]+)"|'([^']+)'|(\S+))"#).unwrap();
@@ -2012,9 +2038,9 @@ fn parse_nushell_config(content: &str) -> ShellConfigEntries {
         if line.is_empty() || line.starts_with('#') {
             continue;
         }
-        if let Some(caps) = alias_re.captures(line) {
+        if let Some(caps) = alias_res.iter().find_map(|re| re.captures(line)) {
             aliases.push((caps[1].to_string(), caps[2].trim().to_string()));
-        } else if let Some(caps) = export_re.captures(line) {
+        } else if let Some(caps) = export_res.iter().find_map(|re| re.captures(line)) {
             exports.push((caps[1].to_string(), caps[2].to_string()));
         } else if let Some(caps) = source_re.captures(line) {
             let path = caps
@@ -2034,6 +2060,941 @@ fn parse_nushell_config(content: &str) -> ShellConfigEntries {
     }
 }
 
+/// Literal value of an export as the shell would see it, quotes and escapes
+/// removed but `$VAR` references left unexpanded. Values that are not a plain
+/// string literal (unterminated quotes, PowerShell expressions) are returned
+/// trimmed as written.
+fn decode_export_value(shell_type: ShellType, raw: &str) -> String {
+    let decoded = match shell_type {
+        ShellType::Bash | ShellType::Zsh => decode_posix_word(raw.trim()),
+        ShellType::Fish => decode_fish_words(raw.trim()),
+        ShellType::PowerShell => decode_powershell_string(raw.trim()),
+        _ => None,
+    };
+    decoded.unwrap_or_else(|| raw.trim().to_string())
+}
+
+/// First POSIX shell word of `raw`: `'...'` is literal, `"..."` honours
+/// backslash escapes of `\`, `"`, `$` and the backtick, and an unquoted
+/// backslash escapes the next character.
+fn decode_posix_word(raw: &str) -> Option<String> {
+    let mut out = String::new();
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => loop {
+                match chars.next()? {
+                    '\'' => break,
+                    c => out.push(c),
+                }
+            },
+            '"' => loop {
+                match chars.next()? {
+                    '"' => break,
+                    '\\' => match chars.next()? {
+                        c @ ('\\' | '"' | '$' | '`') => out.push(c),
+                        c => {
+                            out.push('\\');
+                            out.push(c);
+                        }
+                    },
+                    c => out.push(c),
+                }
+            },
+            '\\' => out.push(chars.next()?),
+            c if c.is_whitespace() || c == ';' => break,
+            c => out.push(c),
+        }
+    }
+    Some(out)
+}
+
+/// Fish words of `raw` joined by spaces (a list variable). Single quotes only
+/// honour `\\` and `\'`; double quotes also honour `\"` and `\$`.
+fn decode_fish_words(raw: &str) -> Option<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' => loop {
+                match chars.next()? {
+                    q if q == c => break,
+                    '\\' => match chars.next()? {
+                        e if e == c || e == '\\' || (c == '"' && e == '$') => word.push(e),
+                        e => {
+                            word.push('\\');
+                            word.push(e);
+                        }
+                    },
+                    q => word.push(q),
+                }
+            },
+            '\\' => word.push(chars.next()?),
+            ';' => break,
+            c if c.is_whitespace() => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            c => word.push(c),
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    Some(words.join(" "))
+}
+
+/// A single PowerShell string literal: `'...'` with `''` for a quote, or
+/// `"..."` with backtick escapes. Anything else is an expression.
+fn decode_powershell_string(raw: &str) -> Option<String> {
+    let mut chars = raw.chars().peekable();
+    let quote = chars.next().filter(|c| *c == '\'' || *c == '"')?;
+    let mut out = String::new();
+    loop {
+        match chars.next()? {
+            c if c == quote => {
+                if chars.peek() == Some(&quote) {
+                    chars.next();
+                    out.push(quote);
+                } else {
+                    break;
+                }
+            }
+            '`' if quote == '"' => match chars.next()? {
+                'n' => out.push('\n'),
+                'r' => out.push('\r'),
+                't' => out.push('\t'),
+                '0' => out.push('\0'),
+                c => out.push(c),
+            },
+            c => out.push(c),
+        }
+    }
+    let rest = chars.collect::<String>();
+    let rest = rest.trim();
+    (rest.is_empty() || rest.starts_with('#') || rest == ";").then_some(out)
+}
+
+/// Regexes recognising alias/export definitions for a shell dialect, in match
+/// priority order. Capture 1 is the name, capture 2 the value.
+fn shell_entry_patterns(shell_type: ShellType, kind: ShellConfigEntryKind) -> Vec<Regex> {
+    let patterns: &[&str] = match (shell_type, kind) {
+        (ShellType::Bash | ShellType::Zsh, ShellConfigEntryKind::Alias) => &[
+            // alias name='value' — single-quoted value (greedy inside quotes)
+            r#"^\s*alias\s+([A-Za-z_][\w-]*)='([^']*)'"#,
+            // alias name="value" — double-quoted value (greedy inside quotes)
+            r#"^\s*alias\s+([A-Za-z_][\w-]*)="([^"]*)""#,
+            // alias name=value — unquoted value
+            r#"^\s*alias\s+([A-Za-z_][\w-]*)=(\S+)\s*$"#,
+        ],
+        // export KEY=VALUE or export KEY="VALUE" or export KEY='VALUE'; the
+        // raw value is decoded by `decode_export_value`
+        (ShellType::Bash | ShellType::Zsh, ShellConfigEntryKind::Export) => {
+            &[r#"^\s*export\s+([A-Za-z_]\w*)=(.*)$"#]
+        }
+        // alias name 'value' or alias name "value" or alias name=value
+        (ShellType::Fish, ShellConfigEntryKind::Alias) => &[
+            r#"^\s*alias\s+(\w+)\s+["']?(.*?)["']?\s*$"#,
+            r#"^\s*alias\s+(\w+)=["']?(.*?)["']?\s*$"#,
+        ],
+        // set -gx KEY VALUE
+        (ShellType::Fish, ShellConfigEntryKind::Export) => &[r#"^\s*set\s+-[gx]+\s+(\w+)\s+(.*)$"#],
+        // Set-Alias / New-Alias / sal / nal -Name name -Value value
+        (ShellType::PowerShell, ShellConfigEntryKind::Alias) => &[
+            r#"(?i)^\s*(?:Set-Alias|New-Alias|sal|nal)\s+(?:-Name\s+)?(\w+)\s+(?:-Value\s+)?["']?([^"'\s]+)["']?"#,
+        ],
+        // $env:KEY = "VALUE"
+        (ShellType::PowerShell, ShellConfigEntryKind::Export) => {
+            &[r#"^\s*\$env:(\w+)\s*=\s*(.*)$"#]
+        }
+        // alias name = value or alias name = { body }
+        (ShellType::Nushell, ShellConfigEntryKind::Alias) => {
+            &[r#"^\s*alias\s+([\w-]+)\s*=\s*(.+)"#]
+        }
+        // $env.KEY = "value" or $env.KEY = value
+        (ShellType::Nushell, ShellConfigEntryKind::Export) => {
+            &[r#"^\s*\$env\.(\w+)\s*=\s*["']?(.*?)["']?\s*$"#]
+        }
+        (ShellType::Cmd, _) => &[],
+    };
+    patterns.iter().map(|p| Regex::new(p).unwrap()).collect()
+}
+
+/// Render a single alias/export definition in the shell's own syntax.
+fn format_config_entry(
+    shell_type: ShellType,
+    kind: ShellConfigEntryKind,
+    name: &str,
+    value: &str,
+) -> CogniaResult<String> {
+    let line = match (shell_type, kind) {
+        (ShellType::Bash | ShellType::Zsh, ShellConfigEntryKind::Alias) => {
+            format!("alias {}='{}'", name, value.replace('\'', r"'\''"))
+        }
+        (ShellType::Bash | ShellType::Zsh, ShellConfigEntryKind::Export) => {
+            format!("export {}='{}'", name, value.replace('\'', r"'\''"))
+        }
+        (ShellType::Fish, ShellConfigEntryKind::Alias) => {
+            format!("alias {} '{}'", name, value.replace('\'', "\\'"))
+        }
+        (ShellType::Fish, ShellConfigEntryKind::Export) => {
+            format!(
+                "set -gx {} '{}'",
+                name,
+                value.replace('\\', r"\\").replace('\'', r"\'")
+            )
+        }
+        (ShellType::PowerShell, ShellConfigEntryKind::Alias) => {
+            if value
+                .chars()
+                .any(|c| c.is_whitespace() || c == '"' || c == '\'')
+            {
+                return Err(CogniaError::Config(
+                    "PowerShell aliases can only point to a single command; use a function for arguments"
+                        .into(),
+                ));
+            }
+            format!("Set-Alias -Name {} -Value {}", name, value)
+        }
+        (ShellType::PowerShell, ShellConfigEntryKind::Export) => {
+            format!("$env:{} = '{}'", name, value.replace('\'', "''"))
+        }
+        (ShellType::Nushell, ShellConfigEntryKind::Alias) => format!("alias {} = {}", name, value),
+        (ShellType::Nushell, ShellConfigEntryKind::Export) => {
+            format!(
+                "$env.{} = \"{}\"",
+                name,
+                value.replace('\\', "\\\\").replace('"', "\\\"")
+            )
+        }
+        (ShellType::Cmd, _) => {
+            return Err(CogniaError::Config(
+                "cmd has no startup config file to edit".into(),
+            ))
+        }
+    };
+    Ok(line)
+}
+
+fn config_entry_name_eq(shell_type: ShellType, a: &str, b: &str) -> bool {
+    // PowerShell alias and variable names are case-insensitive
+    if shell_type == ShellType::PowerShell {
+        a.eq_ignore_ascii_case(b)
+    } else {
+        a == b
+    }
+}
+
+/// Split into lines keeping their terminators, and return the indices of lines
+/// defining `name` (comments are ignored, like `parse_shell_config`).
+fn find_config_entry_lines<'a>(
+    content: &'a str,
+    shell_type: ShellType,
+    kind: ShellConfigEntryKind,
+    name: &str,
+) -> (Vec<&'a str>, Vec<usize>) {
+    let patterns = shell_entry_patterns(shell_type, kind);
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let matches = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| {
+            let line = line.trim();
+            !line.is_empty()
+                && !line.starts_with('#')
+                && patterns
+                    .iter()
+                    .find_map(|re| re.captures(line))
+                    .is_some_and(|caps| config_entry_name_eq(shell_type, &caps[1], name))
+        })
+        .map(|(index, _)| index)
+        .collect();
+    (lines, matches)
+}
+
+/// Insert or replace an alias/export definition. The first existing definition
+/// is rewritten in place (keeping its indentation), later duplicates are
+/// dropped, and the entry is only appended when none exists. Returns the new
+/// content and whether an existing definition was replaced.
+pub fn upsert_config_entry_content(
+    content: &str,
+    shell_type: ShellType,
+    kind: ShellConfigEntryKind,
+    name: &str,
+    value: &str,
+) -> CogniaResult<(String, bool)> {
+    let name = name.trim();
+    if name.is_empty() || name.contains(['\n', '\r']) || value.contains(['\n', '\r']) {
+        return Err(CogniaError::Config(format!(
+            "Invalid {} definition: name is empty or contains line breaks",
+            kind.label()
+        )));
+    }
+    let formatted = format_config_entry(shell_type, kind, name, value)?;
+    // The written line must be found again by the same patterns on the next edit
+    let (_, own) = find_config_entry_lines(&formatted, shell_type, kind, name);
+    if own.is_empty() {
+        return Err(CogniaError::Config(format!(
+            "'{}' is not a valid {} name for {:?}",
+            name,
+            kind.label(),
+            shell_type
+        )));
+    }
+
+    let (lines, matches) = find_config_entry_lines(content, shell_type, kind, name);
+    let Some((&first, duplicates)) = matches.split_first() else {
+        let newline = if content.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        let mut updated = content.to_string();
+        if !updated.is_empty() && !updated.ends_with('\n') {
+            updated.push_str(newline);
+        }
+        updated.push_str(&formatted);
+        updated.push_str(newline);
+        return Ok((updated, false));
+    };
+
+    let mut updated = String::with_capacity(content.len() + formatted.len());
+    for (index, line) in lines.iter().enumerate() {
+        if index == first {
+            let body = line.trim_end_matches(['\r', '\n']);
+            let indent = &body[..body.len() - body.trim_start().len()];
+            updated.push_str(indent);
+            updated.push_str(&formatted);
+            updated.push_str(&line[body.len()..]);
+        } else if !duplicates.contains(&index) {
+            updated.push_str(line);
+        }
+    }
+    Ok((updated, true))
+}
+
+/// Remove every definition of an alias/export. Returns the new content and the
+/// number of removed lines.
+pub fn remove_config_entry_content(
+    content: &str,
+    shell_type: ShellType,
+    kind: ShellConfigEntryKind,
+    name: &str,
+) -> (String, usize) {
+    let (lines, matches) = find_config_entry_lines(content, shell_type, kind, name.trim());
+    let updated = lines
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !matches.contains(index))
+        .map(|(_, line)| *line)
+        .collect();
+    (updated, matches.len())
+}
+
+/// Upsert an alias/export in a config file. Goes through
+/// `write_shell_config_verified`, so the previous file is backed up first.
+pub async fn upsert_shell_config_entry(
+    path: &Path,
+    shell_type: ShellType,
+    kind: ShellConfigEntryKind,
+    name: &str,
+    value: &str,
+) -> CogniaResult<TerminalConfigMutationResult> {
+    let content = if fs::exists(path).await {
+        read_shell_config(path).await?
+    } else {
+        String::new()
+    };
+    let (updated, replaced) = upsert_config_entry_content(&content, shell_type, kind, name, value)
+        .map_err(|e| stage_wrap(TerminalConfigMutationStage::Validation, e))?;
+    let message = format!(
+        "{} {} '{}'",
+        if replaced { "Updated" } else { "Added" },
+        kind.label(),
+        name.trim()
+    );
+
+    let mut result = write_shell_config_verified(path, &updated).await?;
+    result.operation = TerminalConfigMutationOperation::Upsert;
+    result.context_message = Some(message);
+    Ok(result)
+}
+
+/// Remove an alias/export from a config file (backed up before writing).
+pub async fn remove_shell_config_entry(
+    path: &Path,
+    shell_type: ShellType,
+    kind: ShellConfigEntryKind,
+    name: &str,
+) -> CogniaResult<TerminalConfigMutationResult> {
+    let content = read_shell_config(path).await?;
+    let (updated, removed) = remove_config_entry_content(&content, shell_type, kind, name);
+    if removed == 0 {
+        return Err(stage_error(
+            TerminalConfigMutationStage::Validation,
+            format!(
+                "No {} named '{}' in {}",
+                kind.label(),
+                name.trim(),
+                path.display()
+            ),
+        ));
+    }
+
+    let mut result = write_shell_config_verified(path, &updated).await?;
+    result.operation = TerminalConfigMutationOperation::Remove;
+    result.context_message = Some(format!(
+        "Removed {} definition(s) of {} '{}'",
+        removed,
+        kind.label(),
+        name.trim()
+    ));
+    Ok(result)
+}
+
+pub async fn upsert_alias(
+    path: &Path,
+    shell_type: ShellType,
+    name: &str,
+    value: &str,
+) -> CogniaResult<TerminalConfigMutationResult> {
+    upsert_shell_config_entry(path, shell_type, ShellConfigEntryKind::Alias, name, value).await
+}
+
+pub async fn remove_alias(
+    path: &Path,
+    shell_type: ShellType,
+    name: &str,
+) -> CogniaResult<TerminalConfigMutationResult> {
+    remove_shell_config_entry(path, shell_type, ShellConfigEntryKind::Alias, name).await
+}
+
+pub async fn upsert_export(
+    path: &Path,
+    shell_type: ShellType,
+    key: &str,
+    value: &str,
+) -> CogniaResult<TerminalConfigMutationResult> {
+    upsert_shell_config_entry(path, shell_type, ShellConfigEntryKind::Export, key, value).await
+}
+
+pub async fn remove_export(
+    path: &Path,
+    shell_type: ShellType,
+    key: &str,
+) -> CogniaResult<TerminalConfigMutationResult> {
+    remove_shell_config_entry(path, shell_type, ShellConfigEntryKind::Export, key).await
+}
+
+// ============================================================================
+// Shell Profile Analysis
+// ============================================================================
+
+/// Variables Cognia sets when activating managed environments.
+const COGNIA_MANAGED_VARS: &[&str] =
+    &["JAVA_HOME", "GOROOT", "GOPATH", "CARGO_HOME", "RUSTUP_HOME"];
+
+/// Directory marker identifying lines written by Cognia.
+const COGNIA_PROFILE_MARKER: &str = ".cognialauncher";
+
+/// Prefix used when commenting out a line as part of a fix.
+const SHELL_PROFILE_DISABLED_PREFIX: &str = "# Disabled by CogniaLauncher: ";
+
+/// PATH directories that belong to a known tool, so different spellings of
+/// the same install are treated as duplicates.
+const PATH_TOOL_MARKERS: &[(&str, &str)] = &[
+    (".cargo/bin", "cargo"),
+    (".pyenv/", "pyenv"),
+    (".nvm/", "nvm"),
+    (".asdf/", "asdf"),
+    (".volta/", "volta"),
+    (".deno/bin", "deno"),
+    (".bun/bin", "bun"),
+    ("go/bin", "go"),
+];
+
+static QUOTED_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#""([^"]+)"|'([^']+)'"#).unwrap());
+static PYENV_INIT_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"pyenv\s+(?:virtualenv-)?init").unwrap());
+static ASDF_INIT_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"asdf\.(?:sh|fish|nu|ps1)\b").unwrap());
+/// Variable assigned by a line, in any of the supported shells' syntaxes.
+static ASSIGNED_VAR_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?:(?:export\s+)?([A-Za-z_]\w*)=|set\s+(?:-\w+\s+)*([A-Za-z_]\w*)\s|\$env[:.]([A-Za-z_]\w*)\s*=)",
+    )
+    .unwrap()
+});
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ShellProfileFindingKind {
+    DuplicatePathPrepend,
+    ManagedVarOverride,
+    VersionManagerInit,
+}
+
+impl ShellProfileFindingKind {
+    fn slug(self) -> &'static str {
+        match self {
+            ShellProfileFindingKind::DuplicatePathPrepend => "duplicate-path",
+            ShellProfileFindingKind::ManagedVarOverride => "managed-var",
+            ShellProfileFindingKind::VersionManagerInit => "version-manager",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ShellProfileFindingSeverity {
+    Info,
+    Warning,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ShellProfileFixAction {
+    /// Prefix the line with a comment marker
+    CommentOut,
+    /// Move the line above the first Cognia-managed line
+    Reorder,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShellProfileFinding {
+    pub id: String,
+    pub kind: ShellProfileFindingKind,
+    pub severity: ShellProfileFindingSeverity,
+    pub path: String,
+    /// 1-based line number
+    pub line: usize,
+    pub content: String,
+    pub message: String,
+    /// Other lines involved in the conflict (1-based)
+    pub related_lines: Vec<usize>,
+    pub fix: Option<ShellProfileFixAction>,
+    /// Target line for `Reorder` fixes (1-based)
+    pub move_before_line: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShellProfileAnalysis {
+    pub shell_id: String,
+    pub shell_type: ShellType,
+    pub files_scanned: Vec<String>,
+    pub findings: Vec<ShellProfileFinding>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShellProfileFixResult {
+    pub finding_id: String,
+    pub action: ShellProfileFixAction,
+    pub path: String,
+    pub backup_path: String,
+    /// Analysis re-run after the fix was written
+    pub analysis: ShellProfileAnalysis,
+}
+
+fn is_cognia_profile_line(line: &str) -> bool {
+    line.to_lowercase().contains(COGNIA_PROFILE_MARKER)
+}
+
+fn references_path_var(token: &str) -> bool {
+    let lower = token.to_lowercase();
+    lower.contains("$path")
+        || lower.contains("${path}")
+        || lower.contains("$env:path")
+        || lower.contains("$env.path")
+}
+
+/// Split the value of a PATH assignment into the directories it adds.
+fn path_assignment_entries(value: &str, shell_type: ShellType) -> Vec<String> {
+    let strip = |s: &str| s.trim().trim_matches(|c| c == '"' || c == '\'').to_string();
+    let tokens: Vec<String> = match shell_type {
+        ShellType::Fish => value.split_whitespace().map(strip).collect(),
+        ShellType::Nushell if value.contains("prepend") || value.contains("append") => QUOTED_RE
+            .captures_iter(value)
+            .filter_map(|caps| caps.get(1).or(caps.get(2)))
+            .map(|m| m.as_str().to_string())
+            .collect(),
+        ShellType::PowerShell | ShellType::Nushell if value.contains(';') => {
+            value.split(';').map(strip).collect()
+        }
+        _ => value.split(':').map(strip).collect(),
+    };
+
+    tokens
+        .into_iter()
+        .filter(|t| !t.is_empty() && !references_path_var(t))
+        .collect()
+}
+
+/// Key used to group PATH entries: a known tool name, else the normalized dir.
+fn path_tool_key(entry: &str) -> String {
+    let normalized = entry
+        .replace('\\', "/")
+        .replace("${HOME}", "~")
+        .replace("$HOME", "~")
+        .replace("$env:USERPROFILE", "~")
+        .replace("$env.HOME", "~")
+        .trim_end_matches('/')
+        .to_lowercase();
+    PATH_TOOL_MARKERS
+        .iter()
+        .find(|(marker, _)| format!("{}/", normalized).contains(marker))
+        .map(|(_, tool)| tool.to_string())
+        .unwrap_or(normalized)
+}
+
+fn detect_version_manager_init(line: &str) -> Option<&'static str> {
+    let lower = line.to_lowercase();
+    if lower.contains("nvm.sh") || lower.contains("nvm.fish") {
+        Some("nvm")
+    } else if lower.contains("pyenv") && PYENV_INIT_RE.is_match(&lower) {
+        Some("pyenv")
+    } else if lower.contains("asdf") && ASDF_INIT_RE.is_match(&lower) {
+        Some("asdf")
+    } else if lower.contains("volta_home") || lower.contains(".volta/bin") {
+        Some("volta")
+    } else {
+        None
+    }
+}
+
+/// Lines (1-based) of a shell config that initialize a version manager.
+pub fn find_version_manager_inits(content: &str) -> Vec<(usize, &'static str)> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let trimmed = line.trim();
+            !trimmed.is_empty() && !trimmed.starts_with('#')
+        })
+        .filter_map(|(idx, line)| detect_version_manager_init(line).map(|m| (idx + 1, m)))
+        .collect()
+}
+
+fn shell_profile_finding_id(kind: ShellProfileFindingKind, path: &str, line: usize) -> String {
+    let file = Path::new(path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(path);
+    format!("{}:{}:{}", kind.slug(), file, line)
+}
+
+/// Analyze shell config content for conflicts with Cognia-managed PATH
+/// entries and variables. Each line is run through `parse_shell_config`.
+pub fn analyze_shell_profile_content(
+    content: &str,
+    shell_type: ShellType,
+    path: &str,
+) -> Vec<ShellProfileFinding> {
+    let mut findings = Vec::new();
+    if shell_type == ShellType::Cmd {
+        return findings;
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+    let first_cognia_line = lines
+        .iter()
+        .position(|l| !l.trim_start().starts_with('#') && is_cognia_profile_line(l));
+
+    // tool key -> first line that added it
+    let mut path_owners: HashMap<String, usize> = HashMap::new();
+    // var -> line of the Cognia-written assignment
+    let mut cognia_vars: HashMap<String, usize> = HashMap::new();
+
+    let mut push = |kind, severity, idx: usize, message: String, related, fix, move_before| {
+        findings.push(ShellProfileFinding {
+            id: shell_profile_finding_id(kind, path, idx + 1),
+            kind,
+            severity,
+            path: path.to_string(),
+            line: idx + 1,
+            content: lines[idx].to_string(),
+            message,
+            related_lines: related,
+            fix,
+            move_before_line: move_before,
+        });
+    };
+
+    for (idx, raw) in lines.iter().enumerate() {
+        let trimmed = raw.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let from_cognia = is_cognia_profile_line(trimmed);
+        let entries = parse_shell_config(trimmed, shell_type);
+
+        for (key, value) in &entries.exports {
+            let upper = key.to_uppercase();
+            if upper == "PATH" {
+                let added = path_assignment_entries(value, shell_type);
+                let mut duplicates = Vec::new();
+                let mut related = Vec::new();
+                for entry in &added {
+                    let tool = path_tool_key(entry);
+                    match path_owners.get(&tool) {
+                        Some(&first) => {
+                            duplicates.push(tool);
+                            related.push(first + 1);
+                        }
+                        None => {
+                            path_owners.insert(tool, idx);
+                        }
+                    }
+                }
+                if !duplicates.is_empty() {
+                    let all_duplicate = duplicates.len() == added.len();
+                    related.dedup();
+                    push(
+                        ShellProfileFindingKind::DuplicatePathPrepend,
+                        ShellProfileFindingSeverity::Warning,
+                        idx,
+                        format!(
+                            "PATH entry for '{}' was already added on line {}",
+                            duplicates.join("', '"),
+                            related[0]
+                        ),
+                        related,
+                        all_duplicate.then_some(ShellProfileFixAction::CommentOut),
+                        None,
+                    );
+                }
+            } else if COGNIA_MANAGED_VARS.contains(&upper.as_str())
+                || cognia_vars.contains_key(&upper)
+            {
+                if from_cognia {
+                    cognia_vars.insert(upper, idx);
+                    continue;
+                }
+                let (severity, message, related) = match cognia_vars.get(&upper) {
+                    Some(&cognia_idx) => (
+                        ShellProfileFindingSeverity::Warning,
+                        format!(
+                            "{} overrides the value Cognia set on line {}",
+                            upper,
+                            cognia_idx + 1
+                        ),
+                        vec![cognia_idx + 1],
+                    ),
+                    None => (
+                        ShellProfileFindingSeverity::Info,
+                        format!(
+                            "{} is managed by Cognia; this assignment may shadow the active environment",
+                            upper
+                        ),
+                        vec![],
+                    ),
+                };
+                push(
+                    ShellProfileFindingKind::ManagedVarOverride,
+                    severity,
+                    idx,
+                    message,
+                    related,
+                    Some(ShellProfileFixAction::CommentOut),
+                    None,
+                );
+            } else if from_cognia {
+                cognia_vars.insert(upper, idx);
+            }
+        }
+
+        let manager = entries
+            .sources
+            .iter()
+            .find_map(|s| detect_version_manager_init(s))
+            .or_else(|| detect_version_manager_init(trimmed));
+        if let Some(manager) = manager {
+            match first_cognia_line.filter(|&cognia_idx| cognia_idx < idx) {
+                Some(cognia_idx) => push(
+                    ShellProfileFindingKind::VersionManagerInit,
+                    ShellProfileFindingSeverity::Warning,
+                    idx,
+                    format!(
+                        "{} init runs after Cognia's setup on line {} and will shadow Cognia's shims",
+                        manager,
+                        cognia_idx + 1
+                    ),
+                    vec![cognia_idx + 1],
+                    Some(ShellProfileFixAction::Reorder),
+                    Some(cognia_idx + 1),
+                ),
+                None => push(
+                    ShellProfileFindingKind::VersionManagerInit,
+                    ShellProfileFindingSeverity::Info,
+                    idx,
+                    format!(
+                        "{} init manages the same PATH entries as Cognia's shims",
+                        manager
+                    ),
+                    vec![],
+                    Some(ShellProfileFixAction::CommentOut),
+                    None,
+                ),
+            }
+        }
+    }
+
+    findings
+}
+
+/// Name of the variable a shell config line assigns, if any.
+fn assigned_var(line: &str) -> Option<String> {
+    let caps = ASSIGNED_VAR_RE.captures(line)?;
+    caps.get(1)
+        .or(caps.get(2))
+        .or(caps.get(3))
+        .map(|m| m.as_str().to_string())
+}
+
+/// Apply a finding's fix to the content it was produced from.
+pub fn apply_shell_profile_fix_content(
+    content: &str,
+    finding: &ShellProfileFinding,
+) -> CogniaResult<String> {
+    let action = finding.fix.ok_or_else(|| {
+        CogniaError::Config(format!("Finding '{}' has no automatic fix", finding.id))
+    })?;
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+    let idx = finding.line.saturating_sub(1);
+    if lines.get(idx).map(|l| l.as_str()) != Some(finding.content.as_str()) {
+        return Err(CogniaError::Config(format!(
+            "Line {} changed since analysis; re-run the analysis",
+            finding.line
+        )));
+    }
+
+    match action {
+        ShellProfileFixAction::CommentOut => {
+            let line = &lines[idx];
+            let indent_len = line.len() - line.trim_start().len();
+            lines[idx] = format!(
+                "{}{}{}",
+                &line[..indent_len],
+                SHELL_PROFILE_DISABLED_PREFIX,
+                line.trim_start()
+            );
+        }
+        ShellProfileFixAction::Reorder => {
+            let target = finding
+                .move_before_line
+                .map(|l| l.saturating_sub(1))
+                .filter(|&t| t < idx)
+                .ok_or_else(|| {
+                    CogniaError::Config(format!(
+                        "Finding '{}' has no valid reorder target",
+                        finding.id
+                    ))
+                })?;
+            // Assignments just above the init that it reads (like
+            // `export NVM_DIR=...` for nvm.sh) move along with it
+            let mut start = idx;
+            while start > target + 1 {
+                let referenced = assigned_var(&lines[start - 1]).filter(|var| {
+                    lines[start..=idx]
+                        .iter()
+                        .any(|line| line.contains(var.as_str()))
+                });
+                if referenced.is_none() {
+                    break;
+                }
+                start -= 1;
+            }
+            let block: Vec<String> = lines.drain(start..=idx).collect();
+            let rest = lines.split_off(target);
+            lines.extend(block);
+            lines.extend(rest);
+        }
+    }
+
+    let mut output = lines.join("\n");
+    if content.ends_with('\n') {
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+/// Analyze every existing config file of a detected shell.
+pub async fn analyze_shell_profile(shell: &ShellInfo) -> CogniaResult<ShellProfileAnalysis> {
+    let mut files_scanned = Vec::new();
+    let mut findings = Vec::new();
+
+    for file in &shell.config_files {
+        let path = PathBuf::from(&file.path);
+        if !fs::exists(&path).await {
+            continue;
+        }
+        let content = read_shell_config(&path).await?;
+        findings.extend(analyze_shell_profile_content(
+            &content,
+            shell.shell_type,
+            &file.path,
+        ));
+        files_scanned.push(file.path.clone());
+    }
+
+    Ok(ShellProfileAnalysis {
+        shell_id: shell.id.clone(),
+        shell_type: shell.shell_type,
+        files_scanned,
+        findings,
+    })
+}
+
+/// Apply the fix for one finding after backing up the affected file.
+pub async fn apply_shell_profile_fix(
+    shell: &ShellInfo,
+    finding_id: &str,
+) -> CogniaResult<ShellProfileFixResult> {
+    let analysis = analyze_shell_profile(shell).await?;
+    let finding = analysis
+        .findings
+        .iter()
+        .find(|f| f.id == finding_id)
+        .ok_or_else(|| {
+            CogniaError::Config(format!(
+                "Finding '{}' not found; the profile may have changed",
+                finding_id
+            ))
+        })?;
+    let action = finding.fix.ok_or_else(|| {
+        CogniaError::Config(format!("Finding '{}' has no automatic fix", finding_id))
+    })?;
+
+    let path = PathBuf::from(&finding.path);
+    let content = read_shell_config(&path).await?;
+    let updated = apply_shell_profile_fix_content(&content, finding)?;
+
+    let backup_path = backup_shell_config(&path).await?;
+    // Write through a symlinked profile (dotfile managers) rather than
+    // replacing the link with a regular file
+    let target = tokio::fs::canonicalize(&path)
+        .await
+        .unwrap_or_else(|_| path.clone());
+    fs::write_file_atomic(&target, updated.as_bytes()).await?;
+    debug!(
+        "Applied {:?} fix for {} in {}",
+        action,
+        finding_id,
+        path.display()
+    );
+
+    Ok(ShellProfileFixResult {
+        finding_id: finding_id.to_string(),
+        action,
+        path: finding.path.clone(),
+        backup_path: backup_path.display().to_string(),
+        analysis: analyze_shell_profile(shell).await?,
+    })
+}
+
 // ============================================================================
 // PowerShell Management
 // ============================================================================
@@ -2068,6 +3029,60 @@ pub struct PSScriptInfo {
     pub install_path: String,
 }
 
+/// Outcome of updating a single module during an update-all run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PSModuleUpdateStatus {
+    Updated,
+    AlreadyCurrent,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PSModuleUpdateResult {
+    pub name: String,
+    pub installed_version: String,
+    pub latest_version: Option<String>,
+    pub status: PSModuleUpdateStatus,
+    pub error: Option<String>,
+}
+
+/// Progress payload emitted after each module of an update-all run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PSModuleUpdateProgress {
+    pub current: usize,
+    pub total: usize,
+    pub result: PSModuleUpdateResult,
+}
+
+/// An installed module that lists another module in its RequiredModules
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PSModuleDependent {
+    #[serde(alias = "Name")]
+    pub name: String,
+    #[serde(alias = "Version")]
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PSModuleUninstallResult {
+    pub name: String,
+    /// Installed modules that still require the uninstalled module (only with `force`)
+    pub dependents: Vec<PSModuleDependent>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct PSInstalledModule {
+    name: String,
+    version: String,
+}
+
 /// Get the PowerShell executable to use.
 /// Prefers `pwsh` (PowerShell 7+) over `powershell` (Windows PowerShell 5.1)
 /// because 5.1 has known issues with module autoloading (e.g. Microsoft.PowerShell.Security)
@@ -2307,61 +3322,313 @@ pub async fn ps_get_module_detail(name: &str) -> CogniaResult<PSModuleInfo> {
 /// List installed PowerShell scripts
 pub async fn ps_list_installed_scripts() -> CogniaResult<Vec<PSScriptInfo>> {
     let command = r#"
-Get-InstalledScript 2>$null | Select-Object -Property Name,
-    @{N='Version';E={$_.Version.ToString()}},
-    @{N='Author';E={$_.Author}},
-    @{N='Description';E={$_.Description}},
-    @{N='InstallPath';E={$_.InstalledLocation}}
+Get-InstalledScript 2>$null | Select-Object -Property Name,
+    @{N='Version';E={$_.Version.ToString()}},
+    @{N='Author';E={$_.Author}},
+    @{N='Description';E={$_.Description}},
+    @{N='InstallPath';E={$_.InstalledLocation}}
+| ConvertTo-Json -Compress
+"#;
+
+    let output = run_ps_command(command).await?;
+    let output = output.trim();
+    if output.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let scripts: Vec<PSScriptInfo> = if output.starts_with('[') {
+        serde_json::from_str(output)
+    } else {
+        serde_json::from_str(&format!("[{}]", output))
+    }
+    .map_err(|e| CogniaError::Parse(format!("Failed to parse PS scripts: {}", e)))?;
+
+    Ok(scripts)
+}
+
+/// Install a PowerShell module from PSGallery
+pub async fn ps_install_module(name: &str, scope: &str) -> CogniaResult<()> {
+    if scope != "CurrentUser" && scope != "AllUsers" {
+        return Err(CogniaError::Config(
+            "Scope must be 'CurrentUser' or 'AllUsers'".into(),
+        ));
+    }
+
+    let command = format!(
+        "Install-Module -Name '{}' -Scope {} -Force -AllowClobber -ErrorAction Stop",
+        name, scope
+    );
+    run_ps_command_with_timeout(&command, 120).await?;
+    Ok(())
+}
+
+/// Escape a value for use inside a single-quoted PowerShell string
+fn ps_single_quote_escape(value: &str) -> String {
+    value.replace('\'', "''")
+}
+
+/// Parse `Get-Module -ListAvailable` JSON output into unique dependents of `target`
+fn parse_module_dependents(output: &str, target: &str) -> CogniaResult<Vec<PSModuleDependent>> {
+    let output = output.trim();
+    if output.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let modules: Vec<PSModuleDependent> = if output.starts_with('[') {
+        serde_json::from_str(output)
+    } else {
+        serde_json::from_str(&format!("[{}]", output))
+    }
+    .map_err(|e| CogniaError::Parse(format!("Failed to parse module dependents: {}", e)))?;
+
+    // Multiple installed versions of the same dependent collapse into one entry
+    let mut dependents: Vec<PSModuleDependent> = Vec::new();
+    for module in modules {
+        if module.name.eq_ignore_ascii_case(target)
+            || dependents
+                .iter()
+                .any(|d| d.name.eq_ignore_ascii_case(&module.name))
+        {
+            continue;
+        }
+        dependents.push(module);
+    }
+    Ok(dependents)
+}
+
+/// List installed modules whose RequiredModules include `name`
+pub async fn ps_module_dependents(name: &str) -> CogniaResult<Vec<PSModuleDependent>> {
+    let command = format!(
+        r#"
+$target = '{}'
+Get-Module -ListAvailable | Where-Object {{ $_.RequiredModules.Name -contains $target }} |
+    Select-Object -Property Name, @{{N='Version';E={{$_.Version.ToString()}}}}
+| ConvertTo-Json -Compress
+"#,
+        ps_single_quote_escape(name)
+    );
+
+    let output = run_ps_command_with_timeout(&command, 60).await?;
+    parse_module_dependents(&output, name)
+}
+
+/// Uninstall a PowerShell module.
+///
+/// Refuses when other installed modules list it in their RequiredModules,
+/// unless `force` is set, in which case the dependents are reported as warnings.
+pub async fn ps_uninstall_module(name: &str, force: bool) -> CogniaResult<PSModuleUninstallResult> {
+    let dependents = ps_module_dependents(name).await?;
+    let names = dependents
+        .iter()
+        .map(|d| d.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    if !dependents.is_empty() && !force {
+        return Err(CogniaError::Conflict(format!(
+            "Module '{}' is required by: {}. Uninstall those first or use force",
+            name, names
+        )));
+    }
+
+    let command = format!(
+        "Uninstall-Module -Name '{}' -AllVersions -Force -ErrorAction Stop",
+        ps_single_quote_escape(name)
+    );
+    run_ps_command_with_timeout(&command, 60).await?;
+
+    let warnings = if dependents.is_empty() {
+        vec![]
+    } else {
+        vec![format!(
+            "Module '{}' was still required by: {}. These modules may fail to load",
+            name, names
+        )]
+    };
+
+    Ok(PSModuleUninstallResult {
+        name: name.to_string(),
+        dependents,
+        warnings,
+    })
+}
+
+/// Update a PowerShell module to the latest version
+pub async fn ps_update_module(name: &str) -> CogniaResult<()> {
+    let command = format!("Update-Module -Name '{}' -Force -ErrorAction Stop", name);
+    run_ps_command_with_timeout(&command, 120).await?;
+    Ok(())
+}
+
+/// Compare two PowerShell module versions (`1.2.3` or SemVer with a prerelease suffix)
+fn compare_ps_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    fn split(version: &str) -> (Vec<u64>, Option<&str>) {
+        let (release, prerelease) = match version.trim().split_once('-') {
+            Some((release, pre)) => (release, Some(pre)),
+            None => (version.trim(), None),
+        };
+        let parts = release
+            .split('.')
+            .map(|p| p.parse::<u64>().unwrap_or(0))
+            .collect();
+        (parts, prerelease)
+    }
+
+    let (a_parts, a_pre) = split(a);
+    let (b_parts, b_pre) = split(b);
+    let len = a_parts.len().max(b_parts.len());
+    for i in 0..len {
+        let x = a_parts.get(i).copied().unwrap_or(0);
+        let y = b_parts.get(i).copied().unwrap_or(0);
+        match x.cmp(&y) {
+            std::cmp::Ordering::Equal => {}
+            other => return other,
+        }
+    }
+
+    // A release sorts after any prerelease of the same version
+    match (a_pre, b_pre) {
+        (None, None) => std::cmp::Ordering::Equal,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (Some(x), Some(y)) => compare_ps_prereleases(x, y),
+    }
+}
+
+/// Compare SemVer prerelease labels identifier by identifier. Digit runs
+/// compare numerically, so `rc.10` and `rc10` both sort after `rc.9` and `rc9`.
+fn compare_ps_prereleases(a: &str, b: &str) -> std::cmp::Ordering {
+    fn chunks(identifier: &str) -> Vec<&str> {
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        let bytes = identifier.as_bytes();
+        for i in 1..bytes.len() {
+            if bytes[i].is_ascii_digit() != bytes[i - 1].is_ascii_digit() {
+                chunks.push(&identifier[start..i]);
+                start = i;
+            }
+        }
+        if start < identifier.len() {
+            chunks.push(&identifier[start..]);
+        }
+        chunks
+    }
+
+    fn compare_chunk(x: &str, y: &str) -> std::cmp::Ordering {
+        let x_numeric = x.bytes().all(|c| c.is_ascii_digit());
+        let y_numeric = y.bytes().all(|c| c.is_ascii_digit());
+        match (x_numeric, y_numeric) {
+            // Numeric identifiers sort before alphanumeric ones
+            (true, false) => std::cmp::Ordering::Less,
+            (false, true) => std::cmp::Ordering::Greater,
+            (true, true) => {
+                let x = x.trim_start_matches('0');
+                let y = y.trim_start_matches('0');
+                x.len().cmp(&y.len()).then_with(|| x.cmp(y))
+            }
+            (false, false) => x.to_ascii_lowercase().cmp(&y.to_ascii_lowercase()),
+        }
+    }
+
+    // Build metadata does not take part in precedence
+    let a = a.split('+').next().unwrap_or(a);
+    let b = b.split('+').next().unwrap_or(b);
+    let a_chunks: Vec<&str> = a.split('.').flat_map(chunks).collect();
+    let b_chunks: Vec<&str> = b.split('.').flat_map(chunks).collect();
+    for (x, y) in a_chunks.iter().zip(&b_chunks) {
+        match compare_chunk(x, y) {
+            std::cmp::Ordering::Equal => {}
+            other => return other,
+        }
+    }
+    a_chunks.len().cmp(&b_chunks.len())
+}
+
+/// Check PSGallery for a newer version of one module and install it
+async fn ps_update_installed_module(module: &PSInstalledModule) -> PSModuleUpdateResult {
+    let mut result = PSModuleUpdateResult {
+        name: module.name.clone(),
+        installed_version: module.version.clone(),
+        latest_version: None,
+        status: PSModuleUpdateStatus::Failed,
+        error: None,
+    };
+    let name = ps_single_quote_escape(&module.name);
+
+    let find_command = format!(
+        "(Find-Module -Name '{}' -Repository PSGallery -ErrorAction Stop).Version.ToString()",
+        name
+    );
+    let latest = match run_ps_command_with_timeout(&find_command, 60).await {
+        Ok(output) => output.trim().to_string(),
+        Err(e) => {
+            result.error = Some(e.to_string());
+            return result;
+        }
+    };
+    if latest.is_empty() {
+        result.error = Some("Module not found in PSGallery".to_string());
+        return result;
+    }
+    result.latest_version = Some(latest.clone());
+
+    if compare_ps_versions(&latest, &module.version) != std::cmp::Ordering::Greater {
+        result.status = PSModuleUpdateStatus::AlreadyCurrent;
+        return result;
+    }
+
+    let update_command = format!(
+        "Update-Module -Name '{}' -RequiredVersion '{}' -Force -ErrorAction Stop",
+        name,
+        ps_single_quote_escape(&latest)
+    );
+    match run_ps_command_with_timeout(&update_command, 300).await {
+        Ok(_) => result.status = PSModuleUpdateStatus::Updated,
+        Err(e) => result.error = Some(e.to_string()),
+    }
+    result
+}
+
+/// Update every module installed from PSGallery.
+///
+/// Modules are processed one at a time; `on_progress` is called after each one.
+/// Failures are recorded per module and do not abort the run.
+pub async fn ps_update_all_modules<F>(on_progress: F) -> CogniaResult<Vec<PSModuleUpdateResult>>
+where
+    F: Fn(&PSModuleUpdateProgress) + Send + Sync,
+{
+    let command = r#"
+Get-InstalledModule -ErrorAction SilentlyContinue | Where-Object { $_.Repository -eq 'PSGallery' } |
+    Select-Object -Property Name, @{N='Version';E={$_.Version.ToString()}}
 | ConvertTo-Json -Compress
 "#;
 
-    let output = run_ps_command(command).await?;
+    let output = run_ps_command_with_timeout(command, 120).await?;
     let output = output.trim();
     if output.is_empty() {
         return Ok(vec![]);
     }
 
-    let scripts: Vec<PSScriptInfo> = if output.starts_with('[') {
+    let modules: Vec<PSInstalledModule> = if output.starts_with('[') {
         serde_json::from_str(output)
     } else {
         serde_json::from_str(&format!("[{}]", output))
     }
-    .map_err(|e| CogniaError::Parse(format!("Failed to parse PS scripts: {}", e)))?;
-
-    Ok(scripts)
-}
+    .map_err(|e| CogniaError::Parse(format!("Failed to parse installed modules: {}", e)))?;
 
-/// Install a PowerShell module from PSGallery
-pub async fn ps_install_module(name: &str, scope: &str) -> CogniaResult<()> {
-    if scope != "CurrentUser" && scope != "AllUsers" {
-        return Err(CogniaError::Config(
-            "Scope must be 'CurrentUser' or 'AllUsers'".into(),
-        ));
+    let total = modules.len();
+    let mut results = Vec::with_capacity(total);
+    for (index, module) in modules.iter().enumerate() {
+        let result = ps_update_installed_module(module).await;
+        on_progress(&PSModuleUpdateProgress {
+            current: index + 1,
+            total,
+            result: result.clone(),
+        });
+        results.push(result);
     }
 
-    let command = format!(
-        "Install-Module -Name '{}' -Scope {} -Force -AllowClobber -ErrorAction Stop",
-        name, scope
-    );
-    run_ps_command_with_timeout(&command, 120).await?;
-    Ok(())
-}
-
-/// Uninstall a PowerShell module
-pub async fn ps_uninstall_module(name: &str) -> CogniaResult<()> {
-    let command = format!(
-        "Uninstall-Module -Name '{}' -AllVersions -Force -ErrorAction Stop",
-        name
-    );
-    run_ps_command_with_timeout(&command, 60).await?;
-    Ok(())
-}
-
-/// Update a PowerShell module to the latest version
-pub async fn ps_update_module(name: &str) -> CogniaResult<()> {
-    let command = format!("Update-Module -Name '{}' -Force -ErrorAction Stop", name);
-    run_ps_command_with_timeout(&command, 120).await?;
-    Ok(())
+    Ok(results)
 }
 
 /// Search PSGallery for modules matching a query
@@ -3618,6 +4885,182 @@ fn format_size(bytes: u64) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_compare_ps_versions() {
+        use std::cmp::Ordering;
+        assert_eq!(compare_ps_versions("2.2.5", "2.2.5"), Ordering::Equal);
+        assert_eq!(compare_ps_versions("2.10.0", "2.9.1"), Ordering::Greater);
+        assert_eq!(compare_ps_versions("1.0", "1.0.0.0"), Ordering::Equal);
+        assert_eq!(compare_ps_versions("3.0.0-beta1", "3.0.0"), Ordering::Less);
+        assert_eq!(
+            compare_ps_versions("3.0.0", "2.99.0-rc1"),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare_ps_versions("3.0.0-rc.10", "3.0.0-rc.9"),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare_ps_versions("3.0.0-beta2", "3.0.0-beta10"),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_ps_versions("3.0.0-alpha", "3.0.0-alpha.1"),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_ps_versions("3.0.0-alpha.1", "3.0.0-alpha.beta"),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_ps_versions("3.0.0-RC1", "3.0.0-rc1"),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn test_parse_module_dependents_dedups_and_skips_target() {
+        let output = r#"[{"Name":"Az.Compute","Version":"7.1.0"},{"Name":"Az.Compute","Version":"6.0.0"},{"Name":"Az.Network","Version":"7.0.0"},{"Name":"az.accounts","Version":"2.0.0"}]"#;
+        let dependents = parse_module_dependents(output, "Az.Accounts").unwrap();
+        assert_eq!(
+            dependents,
+            vec![
+                PSModuleDependent {
+                    name: "Az.Compute".into(),
+                    version: "7.1.0".into()
+                },
+                PSModuleDependent {
+                    name: "Az.Network".into(),
+                    version: "7.0.0".into()
+                },
+            ]
+        );
+
+        let single =
+            parse_module_dependents(r#"{"Name":"Pester","Version":"5.5.0"}"#, "X").unwrap();
+        assert_eq!(single.len(), 1);
+        assert!(parse_module_dependents("  ", "X").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_ps_single_quote_escape() {
+        assert_eq!(ps_single_quote_escape("O'Brien.Tools"), "O''Brien.Tools");
+    }
+
+    fn finding_kinds(findings: &[ShellProfileFinding]) -> Vec<(ShellProfileFindingKind, usize)> {
+        findings.iter().map(|f| (f.kind, f.line)).collect()
+    }
+
+    #[test]
+    fn test_analyze_shell_profile_posix_conflicts() {
+        let content = r#"export PATH="$HOME/.CogniaLauncher/shims:$PATH"
+export JAVA_HOME="$HOME/.CogniaLauncher/environments/java/21"
+export PATH="$HOME/.cargo/bin:$PATH"
+# export PATH="$HOME/.cargo/bin:$PATH"
+export PATH="${HOME}/.cargo/bin:$PATH"
+export JAVA_HOME=/usr/lib/jvm/java-17
+export NVM_DIR="$HOME/.nvm"
+[ -s "$NVM_DIR/nvm.sh" ] && . "$NVM_DIR/nvm.sh"
+eval "$(pyenv init -)"
+"#;
+        let findings = analyze_shell_profile_content(content, ShellType::Zsh, "/home/u/.zshrc");
+        assert_eq!(
+            finding_kinds(&findings),
+            vec![
+                (ShellProfileFindingKind::DuplicatePathPrepend, 5),
+                (ShellProfileFindingKind::ManagedVarOverride, 6),
+                (ShellProfileFindingKind::VersionManagerInit, 8),
+                (ShellProfileFindingKind::VersionManagerInit, 9),
+            ]
+        );
+        assert_eq!(findings[0].related_lines, vec![3]);
+        assert_eq!(findings[0].id, "duplicate-path:.zshrc:5");
+        assert_eq!(findings[1].severity, ShellProfileFindingSeverity::Warning);
+        assert_eq!(findings[2].fix, Some(ShellProfileFixAction::Reorder));
+        assert_eq!(findings[2].move_before_line, Some(1));
+
+        // The nvm init moves together with the NVM_DIR it reads
+        let reordered = apply_shell_profile_fix_content(content, &findings[2]).unwrap();
+        let lines: Vec<&str> = reordered.lines().collect();
+        assert_eq!(lines[0], "export NVM_DIR=\"$HOME/.nvm\"");
+        assert_eq!(
+            lines[1],
+            "[ -s \"$NVM_DIR/nvm.sh\" ] && . \"$NVM_DIR/nvm.sh\""
+        );
+        assert_eq!(
+            lines[2],
+            "export PATH=\"$HOME/.CogniaLauncher/shims:$PATH\""
+        );
+        assert_eq!(lines.len(), content.lines().count());
+    }
+
+    #[test]
+    fn test_analyze_shell_profile_fish_powershell_nushell() {
+        let fish = "set -gx PATH $HOME/.cargo/bin $PATH\nset -gx PATH ~/.cargo/bin $PATH\nsource ~/.asdf/asdf.fish\n";
+        let findings = analyze_shell_profile_content(fish, ShellType::Fish, "config.fish");
+        assert_eq!(
+            finding_kinds(&findings),
+            vec![
+                (ShellProfileFindingKind::DuplicatePathPrepend, 2),
+                (ShellProfileFindingKind::VersionManagerInit, 3),
+            ]
+        );
+
+        let pwsh = "$env:PATH = \"C:\\Tools\\bin;$env:PATH\"\n$env:PATH = \"C:\\tools\\bin;$env:PATH\"\n$env:GOROOT = \"C:\\Go\"\n";
+        let findings = analyze_shell_profile_content(pwsh, ShellType::PowerShell, "profile.ps1");
+        assert_eq!(
+            finding_kinds(&findings),
+            vec![
+                (ShellProfileFindingKind::DuplicatePathPrepend, 2),
+                (ShellProfileFindingKind::ManagedVarOverride, 3),
+            ]
+        );
+
+        let nu = "$env.PATH = ($env.PATH | prepend '/opt/bin')\n$env.PATH = ($env.PATH | prepend \"/opt/bin\" | prepend \"/usr/local/go/bin\")\n";
+        let findings = analyze_shell_profile_content(nu, ShellType::Nushell, "env.nu");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].line, 2);
+        // Only partly duplicated, so it needs a manual fix
+        assert_eq!(findings[0].fix, None);
+
+        assert!(analyze_shell_profile_content(fish, ShellType::Cmd, "x").is_empty());
+    }
+
+    #[test]
+    fn test_find_version_manager_inits() {
+        let content = "export NVM_DIR=\"$HOME/.nvm\"\n[ -s \"$NVM_DIR/nvm.sh\" ] && . \"$NVM_DIR/nvm.sh\"\n# eval \"$(pyenv init -)\"\nexport VOLTA_HOME=\"$HOME/.volta\"\n. \"$HOME/.asdf/asdf.sh\"\n";
+        assert_eq!(
+            find_version_manager_inits(content),
+            vec![(2, "nvm"), (4, "volta"), (5, "asdf")]
+        );
+    }
+
+    #[test]
+    fn test_apply_shell_profile_fix_content() {
+        let content = "export PATH=\"$HOME/.CogniaLauncher/shims:$PATH\"\n  export GOPATH=/srv/go\neval \"$(pyenv init -)\"\n";
+        let findings = analyze_shell_profile_content(content, ShellType::Bash, ".bashrc");
+        assert_eq!(findings.len(), 2);
+
+        let commented = apply_shell_profile_fix_content(content, &findings[0]).unwrap();
+        assert_eq!(
+            commented.lines().nth(1),
+            Some("  # Disabled by CogniaLauncher: export GOPATH=/srv/go")
+        );
+        assert!(commented.ends_with('\n'));
+
+        let reordered = apply_shell_profile_fix_content(content, &findings[1]).unwrap();
+        assert!(reordered.starts_with("eval \"$(pyenv init -)\"\nexport PATH="));
+        assert!(
+            analyze_shell_profile_content(&reordered, ShellType::Bash, ".bashrc")
+                .iter()
+                .all(|f| f.kind != ShellProfileFindingKind::VersionManagerInit
+                    || f.severity == ShellProfileFindingSeverity::Info)
+        );
+
+        let stale = apply_shell_profile_fix_content("echo hi\n", &findings[0]);
+        assert!(stale.is_err());
+    }
+
     #[test]
     fn test_parse_posix_aliases() {
         let content = r#"
@@ -3728,6 +5171,266 @@ fn test_parse_nushell_config() {
         assert!(entries.sources[1].contains(".env.nu"));
     }
 
+    fn assert_entry_round_trip(
+        shell_type: ShellType,
+        base: &str,
+        (alias, alias_value): (&str, &str),
+        export: &str,
+    ) {
+        let (content, replaced) = upsert_config_entry_content(
+            base,
+            shell_type,
+            ShellConfigEntryKind::Alias,
+            alias,
+            alias_value,
+        )
+        .unwrap();
+        assert!(
+            replaced,
+            "{:?}: existing alias should be replaced",
+            shell_type
+        );
+        let (content, replaced) = upsert_config_entry_content(
+            &content,
+            shell_type,
+            ShellConfigEntryKind::Export,
+            export,
+            "vim",
+        )
+        .unwrap();
+        assert!(
+            replaced,
+            "{:?}: existing export should be replaced",
+            shell_type
+        );
+
+        let entries = parse_shell_config(&content, shell_type);
+        let aliases: Vec<_> = entries.aliases.iter().filter(|(n, _)| n == alias).collect();
+        assert_eq!(aliases.first().map(|(_, v)| v.as_str()), Some(alias_value));
+        let exports: Vec<_> = entries
+            .exports
+            .iter()
+            .filter(|(n, _)| n == export)
+            .collect();
+        assert_eq!(aliases.len(), 1, "{:?}: {}", shell_type, content);
+        assert_eq!(exports.len(), 1, "{:?}: {}", shell_type, content);
+        assert_eq!(exports[0].1, "vim");
+        assert!(
+            content.contains("# keep me"),
+            "{:?}: comment lost",
+            shell_type
+        );
+
+        let (content, removed) =
+            remove_config_entry_content(&content, shell_type, ShellConfigEntryKind::Alias, alias);
+        assert_eq!(removed, 1);
+        let entries = parse_shell_config(&content, shell_type);
+        assert!(entries.aliases.iter().all(|(n, _)| n != alias));
+        assert_eq!(entries.exports.len(), 1);
+    }
+
+    #[test]
+    fn test_config_entry_round_trip_per_dialect() {
+        assert_entry_round_trip(
+            ShellType::Bash,
+            "# keep me\nalias gs='git st'\nexport EDITOR=nano\nalias gs=\"old\"\n",
+            ("gs", "git status"),
+            "EDITOR",
+        );
+        assert_entry_round_trip(
+            ShellType::Fish,
+            "# keep me\nalias gs 'git st'\nset -gx EDITOR nano\n",
+            ("gs", "git status"),
+            "EDITOR",
+        );
+        assert_entry_round_trip(
+            ShellType::PowerShell,
+            "# keep me\nSet-Alias -Name ll -Value ls\n$env:EDITOR = \"nano\"\n",
+            ("ll", "Get-ChildItem"),
+            "EDITOR",
+        );
+        assert_entry_round_trip(
+            ShellType::Nushell,
+            "# keep me\nalias gs = git st\n$env.EDITOR = \"nano\"\n",
+            ("gs", "git status"),
+            "EDITOR",
+        );
+    }
+
+    #[test]
+    fn test_upsert_config_entry_in_place_preserves_layout() {
+        let content = "# aliases\r\nif true; then\r\n  alias ll='ls'\r\nfi\r\n# alias ll='commented'\r\nalias ll='ls -a'\r\n";
+        let (updated, replaced) = upsert_config_entry_content(
+            content,
+            ShellType::Zsh,
+            ShellConfigEntryKind::Alias,
+            "ll",
+            "ls -la",
+        )
+        .unwrap();
+        assert!(replaced);
+        assert_eq!(
+            updated,
+            "# aliases\r\nif true; then\r\n  alias ll='ls -la'\r\nfi\r\n# alias ll='commented'\r\n"
+        );
+    }
+
+    #[test]
+    fn test_upsert_config_entry_appends_when_missing() {
+        let (updated, replaced) = upsert_config_entry_content(
+            "export A=1",
+            ShellType::Bash,
+            ShellConfigEntryKind::Export,
+            "GOPATH",
+            "$HOME/go",
+        )
+        .unwrap();
+        assert!(!replaced);
+        assert_eq!(updated, "export A=1\nexport GOPATH='$HOME/go'\n");
+
+        let (updated, _) = upsert_config_entry_content(
+            "",
+            ShellType::Bash,
+            ShellConfigEntryKind::Alias,
+            "say",
+            "echo it's",
+        )
+        .unwrap();
+        assert_eq!(updated, "alias say='echo it'\\''s'\n");
+    }
+
+    #[test]
+    fn test_format_config_entry_writes_single_quoted_exports() {
+        let export = |shell_type, value| {
+            format_config_entry(shell_type, ShellConfigEntryKind::Export, "V", value).unwrap()
+        };
+        // Values are written literally, never expanded or substituted
+        assert_eq!(
+            export(ShellType::Zsh, r#"$(id) `id` "$HOME" it's"#),
+            r#"export V='$(id) `id` "$HOME" it'\''s'"#
+        );
+        assert_eq!(
+            export(ShellType::Fish, r"C:\dir\ it's $HOME"),
+            r"set -gx V 'C:\\dir\\ it\'s $HOME'"
+        );
+        assert_eq!(
+            export(ShellType::PowerShell, r#"C:\dir\ it's "$env:X" `n"#),
+            r#"$env:V = 'C:\dir\ it''s "$env:X" `n'"#
+        );
+    }
+
+    #[test]
+    fn test_export_values_round_trip_through_parse() {
+        let values = [
+            "plain",
+            "$HOME/go",
+            "$(id) `id`",
+            r#"say "hi""#,
+            r"C:\dir\",
+            "it's",
+            r#"all $ ` " \ ' together"#,
+        ];
+        for shell_type in [
+            ShellType::Bash,
+            ShellType::Zsh,
+            ShellType::Fish,
+            ShellType::PowerShell,
+        ] {
+            for value in values {
+                let (content, _) = upsert_config_entry_content(
+                    "",
+                    shell_type,
+                    ShellConfigEntryKind::Export,
+                    "V",
+                    value,
+                )
+                .unwrap();
+                let entries = parse_shell_config(&content, shell_type);
+                assert_eq!(
+                    entries.exports,
+                    vec![("V".to_string(), value.to_string())],
+                    "{:?}: {}",
+                    shell_type,
+                    content
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_export_value_handles_hand_written_quoting() {
+        assert_eq!(
+            decode_export_value(ShellType::Bash, r#""a\"b\$c" # note"#),
+            r#"a"b$c"#
+        );
+        assert_eq!(decode_export_value(ShellType::Bash, r"C:\\x\ y"), r"C:\x y");
+        assert_eq!(
+            decode_export_value(ShellType::Fish, r#"/a "/b c" '/d\'e'"#),
+            "/a /b c /d'e"
+        );
+        assert_eq!(
+            decode_export_value(ShellType::PowerShell, r#""C:\bin;`"q`"""#),
+            r#"C:\bin;"q""#
+        );
+        // Expressions are kept as written
+        assert_eq!(
+            decode_export_value(ShellType::PowerShell, r#""C:\bin;" + $env:PATH"#),
+            r#""C:\bin;" + $env:PATH"#
+        );
+    }
+
+    #[test]
+    fn test_upsert_config_entry_rejects_invalid_input() {
+        let invalid = [
+            (ShellType::Bash, ShellConfigEntryKind::Export, "1BAD", "x"),
+            (ShellType::Bash, ShellConfigEntryKind::Alias, "ll", "a\nb"),
+            (
+                ShellType::PowerShell,
+                ShellConfigEntryKind::Alias,
+                "ll",
+                "ls -la",
+            ),
+            (ShellType::Cmd, ShellConfigEntryKind::Alias, "ll", "dir"),
+        ];
+        for (shell_type, kind, name, value) in invalid {
+            assert!(
+                upsert_config_entry_content("", shell_type, kind, name, value).is_err(),
+                "{:?} {:?} {} should be rejected",
+                shell_type,
+                kind,
+                name
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upsert_and_remove_shell_config_entry_with_backup() {
+        let dir = std::env::temp_dir().join("cognia_test_config_upsert_entry");
+        let path = dir.join(".bashrc");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(&path, "# base\nalias ll='ls'\n").unwrap();
+
+        let result = upsert_alias(&path, ShellType::Bash, "ll", "ls -la")
+            .await
+            .unwrap();
+        assert_eq!(result.operation, TerminalConfigMutationOperation::Upsert);
+        assert!(result.backup_path.is_some());
+        assert_eq!(
+            read_shell_config(&path).await.unwrap(),
+            "# base\nalias ll='ls -la'\n"
+        );
+
+        let result = remove_alias(&path, ShellType::Bash, "ll").await.unwrap();
+        assert_eq!(result.operation, TerminalConfigMutationOperation::Remove);
+        assert_eq!(read_shell_config(&path).await.unwrap(), "# base\n");
+        assert!(remove_export(&path, ShellType::Bash, "MISSING")
+            .await
+            .is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn test_shell_type_id_roundtrip() {
         for shell in &[
@@ -4480,6 +6183,46 @@ async fn test_create_and_delete_custom_template() {
         let _ = std::fs::remove_dir_all(&dir);
     }
 
+    #[tokio::test]
+    async fn test_restore_profile_keeps_id_and_yields_default() {
+        let dir = std::env::temp_dir().join("cognia_test_profile_restore");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut mgr = TerminalProfileManager::new(&dir).await.unwrap();
+        let profile = TerminalProfile {
+            id: String::new(),
+            name: "Main".into(),
+            shell_id: "bash".into(),
+            args: vec![],
+            env_vars: HashMap::new(),
+            cwd: None,
+            startup_command: None,
+            env_type: None,
+            env_version: None,
+            color: None,
+            is_default: true,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        let id = mgr.create_profile(profile).await.unwrap();
+        let removed = mgr.get_profile(&id).cloned().unwrap();
+        mgr.delete_profile(&id).await.unwrap();
+
+        let mut replacement = removed.clone();
+        replacement.name = "Replacement".into();
+        mgr.create_profile(replacement).await.unwrap();
+
+        let restored_id = mgr.restore_profile(removed.clone()).await.unwrap();
+        assert_eq!(restored_id, id);
+        let restored = mgr.get_profile(&id).unwrap();
+        assert_eq!(restored.created_at, removed.created_at);
+        assert!(!restored.is_default);
+        assert!(mgr.restore_profile(removed).await.is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[tokio::test]
     async fn test_save_profile_as_template() {
         let dir = std::env::temp_dir().join("cognia_test_tpl_save_as");
@@ -4591,6 +6334,40 @@ async fn test_template_persistence_reload() {
         let _ = std::fs::remove_dir_all(&dir);
     }
 
+    #[tokio::test]
+    async fn test_torn_profiles_file_recovers_from_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let profile = |name: &str| TerminalProfile {
+            id: String::new(),
+            name: name.into(),
+            shell_id: "bash".into(),
+            args: Vec::new(),
+            env_vars: HashMap::new(),
+            cwd: None,
+            startup_command: None,
+            env_type: None,
+            env_version: None,
+            color: None,
+            is_default: false,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+
+        let mut mgr = TerminalProfileManager::new(dir.path()).await.unwrap();
+        mgr.create_profile(profile("First")).await.unwrap();
+        mgr.create_profile(profile("Second")).await.unwrap();
+        drop(mgr);
+
+        // Crash halfway through writing profiles.json
+        let path = dir.path().join("terminal").join("profiles.json");
+        let content = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &content[..content.len() / 2]).unwrap();
+
+        let mgr = TerminalProfileManager::new(dir.path()).await.unwrap();
+        let names: Vec<&str> = mgr.profiles.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["First"]);
+    }
+
     #[test]
     fn test_create_profile_from_custom_template() {
         let custom = TerminalProfileTemplate {
@@ -0,0 +1,284 @@
+use crate::core::terminal::TerminalProfile;
+use crate::error::CogniaResult;
+use crate::platform::fs;
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tokio::sync::Mutex;
+
+static LAUNCH_HISTORY_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// One launch of a terminal profile, as resolved at launch time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminalLaunchRecord {
+    pub id: String,
+    pub profile_id: String,
+    pub profile_name: String,
+    /// Executable of the shell the profile resolved to
+    pub shell: String,
+    pub cwd: Option<String>,
+    pub env_type: Option<String>,
+    pub env_version: Option<String>,
+    /// Profile env vars by name; values are `None` unless full recording is on
+    #[serde(default)]
+    pub env_vars: BTreeMap<String, Option<String>>,
+    pub exit_code: Option<i32>,
+    pub success: bool,
+    /// Pinned entries are never dropped by the size cap
+    #[serde(default)]
+    pub pinned: bool,
+    /// Consecutive identical launches folded into this entry
+    #[serde(default = "default_launch_count")]
+    pub launch_count: u32,
+    pub launched_at: String,
+}
+
+fn default_launch_count() -> u32 {
+    1
+}
+
+impl TerminalLaunchRecord {
+    /// Describe a launch of `profile` (with its launch overrides applied)
+    pub fn new(profile: &TerminalProfile, shell: &str, record_env_values: bool) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            profile_id: profile.id.clone(),
+            profile_name: profile.name.clone(),
+            shell: shell.to_string(),
+            cwd: profile.cwd.clone(),
+            env_type: profile.env_type.clone(),
+            env_version: profile.env_version.clone(),
+            env_vars: profile
+                .env_vars
+                .iter()
+                .map(|(key, value)| (key.clone(), record_env_values.then(|| value.clone())))
+                .collect(),
+            exit_code: None,
+            success: false,
+            pinned: false,
+            launch_count: 1,
+            launched_at: Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Whether both records describe the same profile, shell, cwd and env
+    fn same_launch(&self, other: &Self) -> bool {
+        self.profile_id == other.profile_id
+            && self.shell == other.shell
+            && self.cwd == other.cwd
+            && self.env_type == other.env_type
+            && self.env_version == other.env_version
+            && self.env_vars == other.env_vars
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct LaunchHistory {
+    /// Oldest first
+    entries: Vec<TerminalLaunchRecord>,
+}
+
+fn history_path(root_dir: &Path) -> PathBuf {
+    root_dir.join("terminal").join("launch-history.json")
+}
+
+async fn load_history(root_dir: &Path) -> CogniaResult<LaunchHistory> {
+    Ok(fs::read_json_with_backup(history_path(root_dir))
+        .await?
+        .into_value("Terminal launch history")
+        .unwrap_or_default())
+}
+
+async fn save_history(root_dir: &Path, history: &LaunchHistory) -> CogniaResult<()> {
+    fs::atomic_write_json(history_path(root_dir), history, true).await?;
+    Ok(())
+}
+
+/// Keep every pinned entry plus the newest `max_entries` unpinned ones
+fn enforce_cap(entries: &mut Vec<TerminalLaunchRecord>, max_entries: usize) {
+    let unpinned = entries.iter().filter(|e| !e.pinned).count();
+    let mut excess = unpinned.saturating_sub(max_entries);
+    entries.retain(|e| {
+        if excess > 0 && !e.pinned {
+            excess -= 1;
+            false
+        } else {
+            true
+        }
+    });
+}
+
+/// Append a launch, folding it into the newest entry when nothing but the
+/// outcome changed. Returns the stored entry.
+pub async fn record_launch(
+    root_dir: &Path,
+    record: TerminalLaunchRecord,
+    max_entries: u32,
+) -> CogniaResult<TerminalLaunchRecord> {
+    let _guard = LAUNCH_HISTORY_LOCK.lock().await;
+    let mut history = load_history(root_dir).await?;
+
+    let stored = match history.entries.last_mut() {
+        Some(last) if last.same_launch(&record) => {
+            last.exit_code = record.exit_code;
+            last.success = record.success;
+            last.launched_at = record.launched_at;
+            last.launch_count = last.launch_count.saturating_add(1);
+            last.clone()
+        }
+        _ => {
+            history.entries.push(record.clone());
+            record
+        }
+    };
+
+    enforce_cap(&mut history.entries, max_entries as usize);
+    save_history(root_dir, &history).await?;
+    Ok(stored)
+}
+
+/// Launches newest first, optionally limited to one profile
+pub async fn list_launches(
+    root_dir: &Path,
+    limit: Option<usize>,
+    profile_id: Option<&str>,
+) -> CogniaResult<Vec<TerminalLaunchRecord>> {
+    let history = load_history(root_dir).await?;
+    Ok(history
+        .entries
+        .into_iter()
+        .rev()
+        .filter(|e| profile_id.map_or(true, |id| e.profile_id == id))
+        .take(limit.unwrap_or(usize::MAX))
+        .collect())
+}
+
+pub async fn get_launch(root_dir: &Path, id: &str) -> CogniaResult<Option<TerminalLaunchRecord>> {
+    Ok(load_history(root_dir)
+        .await?
+        .entries
+        .into_iter()
+        .find(|e| e.id == id))
+}
+
+/// Pin or unpin an entry. Returns false if `id` is unknown.
+pub async fn set_pinned(
+    root_dir: &Path,
+    id: &str,
+    pinned: bool,
+    max_entries: u32,
+) -> CogniaResult<bool> {
+    let _guard = LAUNCH_HISTORY_LOCK.lock().await;
+    let mut history = load_history(root_dir).await?;
+    let Some(entry) = history.entries.iter_mut().find(|e| e.id == id) else {
+        return Ok(false);
+    };
+    entry.pinned = pinned;
+    enforce_cap(&mut history.entries, max_entries as usize);
+    save_history(root_dir, &history).await?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn profile(id: &str, cwd: &str) -> TerminalProfile {
+        TerminalProfile {
+            id: id.to_string(),
+            name: "Dev".to_string(),
+            shell_id: "bash".to_string(),
+            args: vec![],
+            env_vars: HashMap::from([("TOKEN".to_string(), "secret".to_string())]),
+            cwd: Some(cwd.to_string()),
+            startup_command: None,
+            env_type: None,
+            env_version: None,
+            color: None,
+            is_default: false,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    fn launch(profile_id: &str, cwd: &str, exit_code: i32) -> TerminalLaunchRecord {
+        let mut record = TerminalLaunchRecord::new(&profile(profile_id, cwd), "/bin/bash", false);
+        record.exit_code = Some(exit_code);
+        record.success = exit_code == 0;
+        record
+    }
+
+    #[test]
+    fn test_env_values_recorded_only_when_enabled() {
+        let keys_only = TerminalLaunchRecord::new(&profile("p", "/w"), "sh", false);
+        assert_eq!(keys_only.env_vars.get("TOKEN"), Some(&None));
+
+        let full = TerminalLaunchRecord::new(&profile("p", "/w"), "sh", true);
+        assert_eq!(
+            full.env_vars.get("TOKEN"),
+            Some(&Some("secret".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_record_dedupes_consecutive_launches() {
+        let root = tempfile::tempdir().unwrap();
+        record_launch(root.path(), launch("a", "/w", 0), 10)
+            .await
+            .unwrap();
+        let folded = record_launch(root.path(), launch("a", "/w", 1), 10)
+            .await
+            .unwrap();
+        assert_eq!(folded.launch_count, 2);
+        assert_eq!(folded.exit_code, Some(1));
+
+        record_launch(root.path(), launch("b", "/w", 0), 10)
+            .await
+            .unwrap();
+        record_launch(root.path(), launch("a", "/w", 0), 10)
+            .await
+            .unwrap();
+
+        let all = list_launches(root.path(), None, None).await.unwrap();
+        assert_eq!(
+            all.iter()
+                .map(|e| e.profile_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "b", "a"]
+        );
+        let only_a = list_launches(root.path(), Some(1), Some("a"))
+            .await
+            .unwrap();
+        assert_eq!(only_a.len(), 1);
+        assert_eq!(only_a[0].launch_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_cap_keeps_pinned_entries() {
+        let root = tempfile::tempdir().unwrap();
+        let first = record_launch(root.path(), launch("a", "/1", 0), 2)
+            .await
+            .unwrap();
+        assert!(set_pinned(root.path(), &first.id, true, 2).await.unwrap());
+        assert!(!set_pinned(root.path(), "missing", true, 2).await.unwrap());
+
+        for cwd in ["/2", "/3", "/4"] {
+            record_launch(root.path(), launch("a", cwd, 0), 2)
+                .await
+                .unwrap();
+        }
+
+        let cwds: Vec<_> = list_launches(root.path(), None, None)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| e.cwd.unwrap())
+            .collect();
+        assert_eq!(cwds, vec!["/4", "/3", "/1"]);
+        assert!(get_launch(root.path(), &first.id).await.unwrap().is_some());
+    }
+}
@@ -0,0 +1,506 @@
+//! Interactive PTY-backed terminal sessions.
+//!
+//! Unlike `platform::process::execute_with_streaming`, sessions allocate a real
+//! pseudo-terminal so TTY-detecting tools (`npm init`, REPLs, pagers) behave as
+//! they would in a regular terminal emulator.
+
+use crate::error::{CogniaError, CogniaResult};
+use log::{debug, warn};
+use portable_pty::{native_pty_system, Child, ChildKiller, CommandBuilder, MasterPty, PtySize};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Everything needed to spawn a shell inside a PTY
+#[derive(Debug, Clone, Default)]
+pub struct PtySpawnSpec {
+    pub program: String,
+    pub args: Vec<String>,
+    pub cwd: Option<String>,
+    pub env: HashMap<String, String>,
+    pub cols: u16,
+    pub rows: u16,
+}
+
+/// Public description of a running session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminalSessionInfo {
+    pub session_id: String,
+    pub profile_id: String,
+    pub program: String,
+    pub pid: Option<u32>,
+    pub cols: u16,
+    pub rows: u16,
+    pub created_at: String,
+}
+
+/// Output chunk emitted for a session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminalSessionOutputEvent {
+    pub session_id: String,
+    pub data: String,
+}
+
+/// Emitted once when a session's process exits or the session is closed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminalSessionExitEvent {
+    pub session_id: String,
+    pub exit_code: Option<u32>,
+}
+
+/// Each part has its own lock: a write blocked on a full PTY buffer must not
+/// stop the session from being listed, resized or killed
+struct TerminalSession {
+    info: Mutex<TerminalSessionInfo>,
+    master: Mutex<Box<dyn MasterPty + Send>>,
+    writer: Mutex<Box<dyn Write + Send>>,
+    child: Mutex<Box<dyn Child + Send + Sync>>,
+    killer: Mutex<Box<dyn ChildKiller + Send + Sync>>,
+}
+
+impl TerminalSession {
+    fn terminate(&self) -> Option<u32> {
+        let exited = lock(&self.child)
+            .ok()
+            .and_then(|mut child| child.try_wait().ok().flatten());
+        if let Some(status) = exited {
+            return Some(status.exit_code());
+        }
+        if let Err(e) = lock(&self.killer).and_then(|mut k| k.kill().map_err(CogniaError::Io)) {
+            let session_id = lock(&self.info).map(|i| i.session_id.clone());
+            debug!(
+                "Failed to kill terminal session {}: {}",
+                session_id.unwrap_or_default(),
+                e
+            );
+        }
+        lock(&self.child)
+            .ok()
+            .and_then(|mut child| child.wait().ok())
+            .map(|status| status.exit_code())
+    }
+}
+
+/// Sessions are shared individually so slow PTY I/O on one session never
+/// holds up the map or other sessions
+type SessionHandle = Arc<TerminalSession>;
+type SessionMap = Arc<Mutex<HashMap<String, SessionHandle>>>;
+
+/// Owns all live PTY sessions for the app
+#[derive(Default)]
+pub struct TerminalSessionManager {
+    sessions: SessionMap,
+}
+
+pub type SharedTerminalSessionManager = Arc<TerminalSessionManager>;
+
+impl TerminalSessionManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of currently open sessions
+    pub fn session_count(&self) -> usize {
+        self.sessions.lock().map(|s| s.len()).unwrap_or(0)
+    }
+
+    /// List open sessions
+    pub fn list(&self) -> Vec<TerminalSessionInfo> {
+        let handles: Vec<SessionHandle> = match self.sessions.lock() {
+            Ok(sessions) => sessions.values().cloned().collect(),
+            Err(_) => return Vec::new(),
+        };
+        let mut infos: Vec<_> = handles
+            .iter()
+            .filter_map(|handle| lock(&handle.info).ok().map(|info| info.clone()))
+            .collect();
+        infos.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        infos
+    }
+
+    /// Spawn `spec` in a new PTY and start pumping its output.
+    ///
+    /// `on_output` receives decoded output chunks; `on_exit` fires once when the
+    /// process exits on its own (not when closed via [`Self::close`]).
+    pub fn open<O, E>(
+        &self,
+        profile_id: &str,
+        spec: PtySpawnSpec,
+        max_sessions: usize,
+        on_output: O,
+        on_exit: E,
+    ) -> CogniaResult<TerminalSessionInfo>
+    where
+        O: Fn(TerminalSessionOutputEvent) + Send + 'static,
+        E: FnOnce(TerminalSessionExitEvent) + Send + 'static,
+    {
+        if self.session_count() >= max_sessions {
+            return Err(session_limit_reached(max_sessions));
+        }
+
+        let size = pty_size(spec.cols, spec.rows);
+        let pair = native_pty_system()
+            .openpty(size)
+            .map_err(|e| CogniaError::Internal(format!("Failed to open PTY: {}", e)))?;
+
+        let mut cmd = CommandBuilder::new(&spec.program);
+        cmd.args(&spec.args);
+        if let Some(cwd) = spec.cwd.as_deref().filter(|cwd| !cwd.trim().is_empty()) {
+            cmd.cwd(cwd);
+        }
+        if cmd.get_env("TERM").is_none() {
+            cmd.env("TERM", "xterm-256color");
+        }
+        for (key, value) in &spec.env {
+            cmd.env(key, value);
+        }
+
+        let child = pair.slave.spawn_command(cmd).map_err(|e| {
+            CogniaError::Internal(format!("Failed to spawn '{}': {}", spec.program, e))
+        })?;
+        // The slave end must be dropped so EOF is observed once the child exits
+        drop(pair.slave);
+
+        let reader = pair
+            .master
+            .try_clone_reader()
+            .map_err(|e| CogniaError::Internal(format!("Failed to read PTY: {}", e)))?;
+        let writer = pair
+            .master
+            .take_writer()
+            .map_err(|e| CogniaError::Internal(format!("Failed to write PTY: {}", e)))?;
+
+        let session_id = uuid::Uuid::new_v4().to_string();
+        let info = TerminalSessionInfo {
+            session_id: session_id.clone(),
+            profile_id: profile_id.to_string(),
+            program: spec.program.clone(),
+            pid: child.process_id(),
+            cols: size.cols,
+            rows: size.rows,
+            created_at: chrono::Utc::now().to_rfc3339(),
+        };
+
+        let session = Arc::new(TerminalSession {
+            info: Mutex::new(info.clone()),
+            master: Mutex::new(pair.master),
+            writer: Mutex::new(writer),
+            killer: Mutex::new(child.clone_killer()),
+            child: Mutex::new(child),
+        });
+        {
+            // Re-check under the same lock as the insert so concurrent opens
+            // cannot overshoot the limit
+            let mut sessions = lock(&self.sessions)?;
+            if sessions.len() >= max_sessions {
+                drop(sessions);
+                session.terminate();
+                return Err(session_limit_reached(max_sessions));
+            }
+            sessions.insert(session_id.clone(), session);
+        }
+
+        let sessions = self.sessions.clone();
+        std::thread::Builder::new()
+            .name(format!("pty-{}", &session_id[..8]))
+            .spawn(move || {
+                pump_output(&session_id, reader, &on_output);
+                // Reap only if the session was not closed explicitly
+                let removed = sessions.lock().ok().and_then(|mut s| s.remove(&session_id));
+                if let Some(handle) = removed {
+                    let exit_code = lock(&handle.child)
+                        .ok()
+                        .and_then(|mut child| child.wait().ok())
+                        .map(|status| status.exit_code());
+                    on_exit(TerminalSessionExitEvent {
+                        session_id,
+                        exit_code,
+                    });
+                }
+            })
+            .map_err(|e| CogniaError::Internal(format!("Failed to start PTY reader: {}", e)))?;
+
+        Ok(info)
+    }
+
+    /// Write raw input (keystrokes, pasted text) to a session.
+    ///
+    /// The write can block while the PTY buffer is full, so it runs on the
+    /// blocking pool.
+    pub async fn write(&self, session_id: &str, data: &[u8]) -> CogniaResult<()> {
+        let handle = self.get(session_id)?;
+        let data = data.to_vec();
+        tokio::task::spawn_blocking(move || {
+            let mut writer = lock(&handle.writer)?;
+            writer
+                .write_all(&data)
+                .and_then(|_| writer.flush())
+                .map_err(CogniaError::Io)
+        })
+        .await
+        .map_err(|e| CogniaError::Internal(format!("Terminal write failed: {}", e)))?
+    }
+
+    /// Resize a session's PTY
+    pub fn resize(&self, session_id: &str, cols: u16, rows: u16) -> CogniaResult<()> {
+        let handle = self.get(session_id)?;
+        let size = pty_size(cols, rows);
+        lock(&handle.master)?
+            .resize(size)
+            .map_err(|e| CogniaError::Internal(format!("Failed to resize PTY: {}", e)))?;
+        let mut info = lock(&handle.info)?;
+        info.cols = size.cols;
+        info.rows = size.rows;
+        Ok(())
+    }
+
+    /// Kill a session's process and release its PTY.
+    ///
+    /// Waiting for the process to exit runs on the blocking pool.
+    pub async fn close(&self, session_id: &str) -> CogniaResult<Option<u32>> {
+        let removed = lock(&self.sessions)?.remove(session_id);
+        let handle = removed.ok_or_else(|| session_not_found(session_id))?;
+        tokio::task::spawn_blocking(move || handle.terminate())
+            .await
+            .map_err(|e| CogniaError::Internal(format!("Terminal close failed: {}", e)))
+    }
+
+    /// Kill every open session. Called on app shutdown so no shells are orphaned.
+    pub fn close_all(&self) -> usize {
+        let drained: Vec<SessionHandle> = match self.sessions.lock() {
+            Ok(mut sessions) => sessions.drain().map(|(_, s)| s).collect(),
+            Err(_) => return 0,
+        };
+        let count = drained.len();
+        for handle in drained {
+            handle.terminate();
+        }
+        if count > 0 {
+            warn!("Terminated {} orphaned terminal session(s)", count);
+        }
+        count
+    }
+
+    /// Look up a session, releasing the map lock before returning
+    fn get(&self, session_id: &str) -> CogniaResult<SessionHandle> {
+        lock(&self.sessions)?
+            .get(session_id)
+            .cloned()
+            .ok_or_else(|| session_not_found(session_id))
+    }
+}
+
+impl Drop for TerminalSessionManager {
+    fn drop(&mut self) {
+        self.close_all();
+    }
+}
+
+fn lock<T: ?Sized>(mutex: &Mutex<T>) -> CogniaResult<MutexGuard<'_, T>> {
+    mutex
+        .lock()
+        .map_err(|_| CogniaError::Internal("Terminal session lock poisoned".into()))
+}
+
+fn session_limit_reached(max_sessions: usize) -> CogniaError {
+    CogniaError::Conflict(format!(
+        "Maximum number of terminal sessions ({}) reached",
+        max_sessions
+    ))
+}
+
+fn session_not_found(session_id: &str) -> CogniaError {
+    CogniaError::Config(format!("Terminal session '{}' not found", session_id))
+}
+
+fn pty_size(cols: u16, rows: u16) -> PtySize {
+    PtySize {
+        rows: rows.clamp(1, 500),
+        cols: cols.clamp(1, 1000),
+        pixel_width: 0,
+        pixel_height: 0,
+    }
+}
+
+/// Read PTY output until EOF, emitting UTF-8 chunks.
+/// Incomplete multi-byte sequences are carried into the next read.
+fn pump_output<O>(session_id: &str, mut reader: Box<dyn Read + Send>, on_output: &O)
+where
+    O: Fn(TerminalSessionOutputEvent),
+{
+    let mut buf = [0u8; 8192];
+    let mut pending: Vec<u8> = Vec::new();
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => n,
+        };
+        pending.extend_from_slice(&buf[..n]);
+        let (text, rest) = split_utf8_prefix(&pending);
+        if !text.is_empty() {
+            on_output(TerminalSessionOutputEvent {
+                session_id: session_id.to_string(),
+                data: text,
+            });
+        }
+        pending = rest;
+    }
+    if !pending.is_empty() {
+        on_output(TerminalSessionOutputEvent {
+            session_id: session_id.to_string(),
+            data: String::from_utf8_lossy(&pending).to_string(),
+        });
+    }
+}
+
+/// Split bytes into the longest decodable prefix and a trailing incomplete sequence
+fn split_utf8_prefix(bytes: &[u8]) -> (String, Vec<u8>) {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => (text.to_string(), Vec::new()),
+        Err(e) if e.error_len().is_none() => {
+            let valid = e.valid_up_to();
+            (
+                String::from_utf8_lossy(&bytes[..valid]).to_string(),
+                bytes[valid..].to_vec(),
+            )
+        }
+        Err(_) => (String::from_utf8_lossy(bytes).to_string(), Vec::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_utf8_prefix_keeps_incomplete_tail() {
+        let bytes = "héllo".as_bytes();
+        // Cut in the middle of 'é' (2 bytes)
+        let (text, rest) = split_utf8_prefix(&bytes[..2]);
+        assert_eq!(text, "h");
+        assert_eq!(rest, vec![bytes[1]]);
+
+        let (text, rest) = split_utf8_prefix(bytes);
+        assert_eq!(text, "héllo");
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn test_pty_size_is_clamped() {
+        let size = pty_size(0, 0);
+        assert_eq!((size.cols, size.rows), (1, 1));
+        let size = pty_size(120, 40);
+        assert_eq!((size.cols, size.rows), (120, 40));
+    }
+
+    #[tokio::test]
+    async fn test_missing_session_operations_fail() {
+        let manager = TerminalSessionManager::new();
+        assert!(manager.write("missing", b"ls\n").await.is_err());
+        assert!(manager.resize("missing", 80, 24).is_err());
+        assert!(manager.close("missing").await.is_err());
+        assert_eq!(manager.close_all(), 0);
+    }
+
+    #[test]
+    fn test_open_rejects_when_limit_reached() {
+        let manager = TerminalSessionManager::new();
+        let err = manager
+            .open(
+                "profile",
+                PtySpawnSpec {
+                    program: "sh".into(),
+                    cols: 80,
+                    rows: 24,
+                    ..Default::default()
+                },
+                0,
+                |_| {},
+                |_| {},
+            )
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Maximum number of terminal sessions"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_open_write_and_close_session() {
+        let manager = TerminalSessionManager::new();
+        let (tx, rx) = std::sync::mpsc::channel::<String>();
+        let info = manager
+            .open(
+                "profile",
+                PtySpawnSpec {
+                    program: "sh".into(),
+                    cols: 80,
+                    rows: 24,
+                    ..Default::default()
+                },
+                4,
+                move |event| {
+                    let _ = tx.send(event.data);
+                },
+                |_| {},
+            )
+            .unwrap();
+        assert_eq!(manager.list().len(), 1);
+
+        manager
+            .write(&info.session_id, b"echo cognia-pty-ok\n")
+            .await
+            .unwrap();
+        let mut output = String::new();
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while std::time::Instant::now() < deadline && !output.contains("cognia-pty-ok\r\n") {
+            if let Ok(chunk) = rx.recv_timeout(std::time::Duration::from_millis(200)) {
+                output.push_str(&chunk);
+            }
+        }
+        assert!(output.contains("cognia-pty-ok"));
+
+        manager.resize(&info.session_id, 100, 30).unwrap();
+        manager.close(&info.session_id).await.unwrap();
+        assert_eq!(manager.session_count(), 0);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_close_does_not_wait_for_pending_write() {
+        let manager = TerminalSessionManager::new();
+        let info = manager
+            .open(
+                "profile",
+                PtySpawnSpec {
+                    program: "sh".into(),
+                    cols: 80,
+                    rows: 24,
+                    ..Default::default()
+                },
+                4,
+                |_| {},
+                |_| {},
+            )
+            .unwrap();
+
+        // Stand in for a write stuck on a full PTY buffer
+        let handle = manager.get(&info.session_id).unwrap();
+        let _writer = handle.writer.lock().unwrap();
+
+        assert_eq!(manager.list().len(), 1);
+        manager.resize(&info.session_id, 100, 30).unwrap();
+        tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            manager.close(&info.session_id),
+        )
+        .await
+        .expect("close blocked on the writer lock")
+        .unwrap();
+        assert_eq!(manager.session_count(), 0);
+    }
+}
@@ -0,0 +1,433 @@
+//! Application trash for objects removed by destructive commands.
+//!
+//! Deleting a terminal profile, custom detection rule, shim or environment
+//! profile serializes the removed object into `<root>/trash/trash.json`
+//! first, so it can be restored until the retention period purges it.
+
+use super::shim::{ShimConfig, ShimManager};
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::fs;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::sync::Mutex;
+
+/// How long trashed objects are kept when the setting is left alone
+pub const DEFAULT_TRASH_RETENTION_DAYS: u32 = 30;
+static TRASH_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrashKind {
+    TerminalProfile,
+    CustomRule,
+    Shim,
+    EnvironmentProfile,
+}
+
+/// A removed object and where it came from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashEntry {
+    pub id: String,
+    pub kind: TrashKind,
+    /// ID the object had when it was deleted (binary name for shims)
+    pub object_id: String,
+    /// Display name at deletion time
+    pub label: String,
+    pub deleted_at: String,
+    /// The object exactly as its manager stores it
+    pub payload: serde_json::Value,
+}
+
+impl TrashEntry {
+    pub fn new<T: Serialize>(
+        kind: TrashKind,
+        object_id: &str,
+        label: &str,
+        object: &T,
+        now: DateTime<Utc>,
+    ) -> CogniaResult<Self> {
+        let payload = serde_json::to_value(object).map_err(|e| {
+            CogniaError::Parse(format!("Failed to serialize trashed object: {}", e))
+        })?;
+        Ok(Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            kind,
+            object_id: object_id.to_string(),
+            label: label.to_string(),
+            deleted_at: now.to_rfc3339(),
+            payload,
+        })
+    }
+
+    /// The trashed object, deserialized back into its manager's type
+    pub fn object<T: DeserializeOwned>(&self) -> CogniaResult<T> {
+        serde_json::from_value(self.payload.clone()).map_err(|e| {
+            CogniaError::Parse(format!("Failed to read trash entry {}: {}", self.id, e))
+        })
+    }
+
+    fn deleted_before(&self, cutoff: DateTime<Utc>) -> bool {
+        DateTime::parse_from_rfc3339(&self.deleted_at)
+            .map(|deleted| deleted < cutoff)
+            .unwrap_or(true)
+    }
+}
+
+/// Outcome of restoring a trash entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashRestoreResult {
+    pub entry_id: String,
+    pub kind: TrashKind,
+    /// ID the object was restored under
+    pub restored_id: String,
+    /// The original ID was taken again, so `restored_id` carries a suffix
+    pub renamed: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct TrashStore {
+    entries: Vec<TrashEntry>,
+}
+
+fn store_path(root_dir: &Path) -> PathBuf {
+    root_dir.join("trash").join("trash.json")
+}
+
+async fn load_store(root_dir: &Path) -> CogniaResult<TrashStore> {
+    Ok(fs::read_json_with_backup(store_path(root_dir))
+        .await?
+        .into_value("Trash")
+        .unwrap_or_default())
+}
+
+async fn save_store(root_dir: &Path, store: &TrashStore) -> CogniaResult<()> {
+    fs::atomic_write_json(store_path(root_dir), store, true).await?;
+    Ok(())
+}
+
+/// Add `entry` to the trash
+pub async fn put(root_dir: &Path, entry: TrashEntry) -> CogniaResult<TrashEntry> {
+    let _guard = TRASH_LOCK.lock().await;
+    let mut store = load_store(root_dir).await?;
+    store.entries.push(entry.clone());
+    save_store(root_dir, &store).await?;
+    Ok(entry)
+}
+
+/// All trashed objects, most recently deleted first
+pub async fn list_entries(root_dir: &Path) -> CogniaResult<Vec<TrashEntry>> {
+    let mut entries = load_store(root_dir).await?.entries;
+    entries.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    Ok(entries)
+}
+
+pub async fn get_entry(root_dir: &Path, id: &str) -> CogniaResult<TrashEntry> {
+    load_store(root_dir)
+        .await?
+        .entries
+        .into_iter()
+        .find(|e| e.id == id)
+        .ok_or_else(|| CogniaError::Config(format!("Trash entry '{}' not found", id)))
+}
+
+/// Take an entry out of the trash to restore it. Only one caller gets a
+/// given entry, so it cannot be restored twice; a failed restore gives it
+/// back with [`put`].
+pub async fn take_entry(root_dir: &Path, id: &str) -> CogniaResult<TrashEntry> {
+    let _guard = TRASH_LOCK.lock().await;
+    let mut store = load_store(root_dir).await?;
+    let index = store
+        .entries
+        .iter()
+        .position(|e| e.id == id)
+        .ok_or_else(|| CogniaError::Config(format!("Trash entry '{}' not found", id)))?;
+    let entry = store.entries.remove(index);
+    save_store(root_dir, &store).await?;
+    Ok(entry)
+}
+
+/// Recreate a trashed shim. Shims are named after the command they run, so
+/// a shim that took the name since is refused instead of renamed.
+pub async fn restore_shim(root_dir: &Path, entry: &TrashEntry) -> CogniaResult<String> {
+    let config: ShimConfig = entry.object()?;
+    let mut manager = ShimManager::new(root_dir).await?;
+    if let Some(existing) = manager.get_shim(&config.binary_name) {
+        return Err(CogniaError::Conflict(format!(
+            "A shim for '{}' ({}) already exists; remove it before restoring this one",
+            existing.binary_name, existing.env_type
+        )));
+    }
+    let binary_name = config.binary_name.clone();
+    manager.create_shim(config).await?;
+    Ok(binary_name)
+}
+
+/// Drop an entry once it was restored, or when the deletion it recorded
+/// failed. Returns false if `id` is unknown.
+pub async fn remove_entry(root_dir: &Path, id: &str) -> CogniaResult<bool> {
+    let _guard = TRASH_LOCK.lock().await;
+    let mut store = load_store(root_dir).await?;
+    let before = store.entries.len();
+    store.entries.retain(|e| e.id != id);
+    if store.entries.len() == before {
+        return Ok(false);
+    }
+    save_store(root_dir, &store).await?;
+    Ok(true)
+}
+
+/// Permanently delete entries trashed before `older_than`, or every entry
+/// when it is `None`. Returns the number of entries removed.
+pub async fn purge(root_dir: &Path, older_than: Option<DateTime<Utc>>) -> CogniaResult<usize> {
+    let _guard = TRASH_LOCK.lock().await;
+    let mut store = load_store(root_dir).await?;
+    let before = store.entries.len();
+    match older_than {
+        Some(cutoff) => store.entries.retain(|e| !e.deleted_before(cutoff)),
+        None => store.entries.clear(),
+    }
+    let purged = before - store.entries.len();
+    if purged > 0 {
+        save_store(root_dir, &store).await?;
+    }
+    Ok(purged)
+}
+
+/// `original` if it is free, otherwise the first free `original-restored`,
+/// `original-restored-2`, ...
+pub fn restore_id(original: &str, is_taken: impl Fn(&str) -> bool) -> String {
+    if !is_taken(original) {
+        return original.to_string();
+    }
+    let base = format!("{}-restored", original);
+    if !is_taken(&base) {
+        return base;
+    }
+    (2..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|candidate| !is_taken(candidate))
+        .expect("unbounded suffix search")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::custom_detection::{CustomDetectionRule, ExtractionStrategy};
+    use crate::core::profiles::{EnvironmentProfile, ProfileEnvironment};
+    use crate::core::terminal::TerminalProfile;
+    use chrono::Duration;
+    use std::collections::HashMap;
+    use tempfile::tempdir;
+
+    fn assert_round_trip<T: Serialize + DeserializeOwned>(kind: TrashKind, object: &T) {
+        let entry = TrashEntry::new(kind, "id", "label", object, Utc::now()).unwrap();
+        let restored: T = entry.object().unwrap();
+        assert_eq!(
+            serde_json::to_value(&restored).unwrap(),
+            serde_json::to_value(object).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_round_trip_preserves_every_field() {
+        assert_round_trip(
+            TrashKind::TerminalProfile,
+            &TerminalProfile {
+                id: "t1".into(),
+                name: "Dev shell".into(),
+                shell_id: "zsh".into(),
+                args: vec!["-l".into()],
+                env_vars: HashMap::from([("FOO".into(), "bar".into())]),
+                cwd: Some("/work".into()),
+                startup_command: Some("ls".into()),
+                env_type: Some("node".into()),
+                env_version: Some("20.11.0".into()),
+                color: Some("#ff0000".into()),
+                is_default: true,
+                created_at: "2024-01-01T00:00:00Z".into(),
+                updated_at: "2024-02-01T00:00:00Z".into(),
+            },
+        );
+        assert_round_trip(
+            TrashKind::CustomRule,
+            &CustomDetectionRule {
+                id: "rule".into(),
+                name: "Pinned node".into(),
+                description: Some("reads .node-pin".into()),
+                env_type: "node".into(),
+                priority: 5,
+                enabled: false,
+                file_patterns: vec![".node-pin".into()],
+                extraction: ExtractionStrategy::PlainText {
+                    strip_prefix: Some("v".into()),
+                    strip_suffix: None,
+                },
+                version_transform: None,
+                tags: vec!["team".into()],
+                created_at: Some("2024-01-01T00:00:00Z".into()),
+                updated_at: None,
+                approved_command: None,
+            },
+        );
+        assert_round_trip(
+            TrashKind::Shim,
+            &ShimConfig {
+                env_type: "node".into(),
+                binary_name: "node".into(),
+                version: Some("20.11.0".into()),
+                target_path: PathBuf::from("/opt/node/bin/node"),
+                direct: false,
+                version_switch: false,
+            },
+        );
+        let mut profile = EnvironmentProfile::new("Work");
+        profile.environments.push(ProfileEnvironment {
+            env_type: "python".into(),
+            version: "3.12.1".into(),
+            provider_id: Some("pyenv".into()),
+        });
+        assert_round_trip(TrashKind::EnvironmentProfile, &profile);
+    }
+
+    #[tokio::test]
+    async fn test_put_list_remove() {
+        let dir = tempdir().unwrap();
+        let shim = ShimConfig {
+            env_type: "go".into(),
+            binary_name: "go".into(),
+            version: None,
+            target_path: PathBuf::from("/usr/local/go/bin/go"),
+            direct: false,
+            version_switch: false,
+        };
+        let now = Utc::now();
+        let older = put(
+            dir.path(),
+            TrashEntry::new(TrashKind::Shim, "go", "go", &shim, now - Duration::hours(1)).unwrap(),
+        )
+        .await
+        .unwrap();
+        let newer = put(
+            dir.path(),
+            TrashEntry::new(TrashKind::Shim, "gofmt", "gofmt", &shim, now).unwrap(),
+        )
+        .await
+        .unwrap();
+
+        let entries = list_entries(dir.path()).await.unwrap();
+        let ids: Vec<&str> = entries.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec![newer.id.as_str(), older.id.as_str()]);
+
+        let fetched = get_entry(dir.path(), &older.id).await.unwrap();
+        assert_eq!(fetched.object::<ShimConfig>().unwrap().binary_name, "go");
+
+        assert!(remove_entry(dir.path(), &older.id).await.unwrap());
+        assert!(!remove_entry(dir.path(), &older.id).await.unwrap());
+        assert!(get_entry(dir.path(), &older.id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_purge_older_than_and_all() {
+        let dir = tempdir().unwrap();
+        let now = Utc::now();
+        for age in [40, 10, 0] {
+            let entry = TrashEntry::new(
+                TrashKind::CustomRule,
+                "rule",
+                "rule",
+                &serde_json::json!({}),
+                now - Duration::days(age),
+            )
+            .unwrap();
+            put(dir.path(), entry).await.unwrap();
+        }
+
+        let cutoff = now - Duration::days(DEFAULT_TRASH_RETENTION_DAYS as i64);
+        assert_eq!(purge(dir.path(), Some(cutoff)).await.unwrap(), 1);
+        assert_eq!(list_entries(dir.path()).await.unwrap().len(), 2);
+        assert_eq!(purge(dir.path(), None).await.unwrap(), 2);
+        assert!(list_entries(dir.path()).await.unwrap().is_empty());
+    }
+
+    fn shim(binary_name: &str, env_type: &str) -> ShimConfig {
+        ShimConfig {
+            env_type: env_type.into(),
+            binary_name: binary_name.into(),
+            version: Some("1.0.0".into()),
+            target_path: PathBuf::from("/opt/tool/bin").join(binary_name),
+            direct: false,
+            version_switch: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_take_entry_hands_out_an_entry_once() {
+        let dir = tempdir().unwrap();
+        let entry = put(
+            dir.path(),
+            TrashEntry::new(TrashKind::Shim, "go", "go", &shim("go", "go"), Utc::now()).unwrap(),
+        )
+        .await
+        .unwrap();
+
+        let (first, second) = tokio::join!(
+            take_entry(dir.path(), &entry.id),
+            take_entry(dir.path(), &entry.id)
+        );
+        assert!(first.is_ok() != second.is_ok());
+        assert!(list_entries(dir.path()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_restore_shim_recreates_it() {
+        let dir = tempdir().unwrap();
+        let entry = TrashEntry::new(
+            TrashKind::Shim,
+            "node",
+            "node",
+            &shim("node", "node"),
+            Utc::now(),
+        )
+        .unwrap();
+
+        assert_eq!(restore_shim(dir.path(), &entry).await.unwrap(), "node");
+        let manager = ShimManager::new(dir.path()).await.unwrap();
+        let restored = manager.get_shim("node").unwrap();
+        assert_eq!(restored.version.as_deref(), Some("1.0.0"));
+        #[cfg(unix)]
+        assert!(dir.path().join("shims").join("node").exists());
+    }
+
+    #[tokio::test]
+    async fn test_restore_shim_refuses_taken_name() {
+        let dir = tempdir().unwrap();
+        let mut manager = ShimManager::new(dir.path()).await.unwrap();
+        manager.create_shim(shim("python", "conda")).await.unwrap();
+        let entry = TrashEntry::new(
+            TrashKind::Shim,
+            "python",
+            "python",
+            &shim("python", "python"),
+            Utc::now(),
+        )
+        .unwrap();
+
+        let err = restore_shim(dir.path(), &entry).await.unwrap_err();
+        assert!(matches!(err, CogniaError::Conflict(_)));
+        let manager = ShimManager::new(dir.path()).await.unwrap();
+        assert_eq!(manager.get_shim("python").unwrap().env_type, "conda");
+        assert!(manager.get_shim("python-restored").is_none());
+    }
+
+    #[test]
+    fn test_restore_id_adds_suffix_on_collision() {
+        let taken = ["a", "a-restored", "a-restored-2"];
+        assert_eq!(restore_id("b", |id| taken.contains(&id)), "b");
+        assert_eq!(restore_id("a", |id| taken[..1].contains(&id)), "a-restored");
+        assert_eq!(restore_id("a", |id| taken.contains(&id)), "a-restored-3");
+    }
+}
@@ -788,6 +788,7 @@ async fn list_installed(
                     install_path: PathBuf::from("."),
                     installed_at: chrono::Utc::now().to_rfc3339(),
                     is_global: true,
+                    kind: None,
                 })
                 .collect())
         }
@@ -0,0 +1,777 @@
+//! Import of versions installed by other version managers.
+//!
+//! Discovers nvm, pyenv, sdkman and asdf installs on disk and keeps a
+//! registry of the ones adopted in place. Adopted installs stay owned by
+//! their original manager: Cognia only links and shims them, and never
+//! deletes their files unless explicitly asked to.
+
+use super::shim::{ShimConfig, ShimManager};
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::fs;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Version manager whose installs can be imported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForeignManager {
+    Nvm,
+    Pyenv,
+    Sdkman,
+    Asdf,
+}
+
+impl ForeignManager {
+    pub fn all() -> [ForeignManager; 4] {
+        [Self::Nvm, Self::Pyenv, Self::Sdkman, Self::Asdf]
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Nvm => "nvm",
+            Self::Pyenv => "pyenv",
+            Self::Sdkman => "sdkman",
+            Self::Asdf => "asdf",
+        }
+    }
+
+    fn root_env_var(self) -> &'static str {
+        match self {
+            Self::Nvm => "NVM_DIR",
+            Self::Pyenv => "PYENV_ROOT",
+            Self::Sdkman => "SDKMAN_DIR",
+            Self::Asdf => "ASDF_DATA_DIR",
+        }
+    }
+
+    fn default_root_name(self) -> &'static str {
+        match self {
+            Self::Nvm => ".nvm",
+            Self::Pyenv => ".pyenv",
+            Self::Sdkman => ".sdkman",
+            Self::Asdf => ".asdf",
+        }
+    }
+
+    /// Manager data directory, honouring the manager's own env variable
+    pub fn root_dir(self) -> Option<PathBuf> {
+        std::env::var_os(self.root_env_var())
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| fs::get_home_dir().map(|home| home.join(self.default_root_name())))
+    }
+
+    /// Whether a Cognia provider manages this manager's own install tree.
+    /// Such installs are not foreign to that provider.
+    pub fn owns_provider(self, provider_id: &str) -> bool {
+        match self {
+            Self::Sdkman => provider_id == "sdkman" || provider_id.starts_with("sdkman-"),
+            _ => provider_id == self.as_str(),
+        }
+    }
+}
+
+/// A version found in another manager's install tree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForeignInstall {
+    pub manager: ForeignManager,
+    pub env_type: String,
+    pub version: String,
+    pub install_path: PathBuf,
+    /// Directory holding the executables (`bin/` when present)
+    pub bin_dir: PathBuf,
+    /// Already registered as an adopted install
+    #[serde(default)]
+    pub adopted: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForeignManagerInventory {
+    pub manager: ForeignManager,
+    pub root: Option<PathBuf>,
+    pub detected: bool,
+    pub install_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForeignEnvInventory {
+    pub env_type: String,
+    pub installs: Vec<ForeignInstall>,
+}
+
+/// Everything discovered across the supported managers, grouped by
+/// environment type
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionImportInventory {
+    pub managers: Vec<ForeignManagerInventory>,
+    pub env_types: Vec<ForeignEnvInventory>,
+}
+
+impl VersionImportInventory {
+    pub fn installs(&self) -> impl Iterator<Item = &ForeignInstall> {
+        self.env_types.iter().flat_map(|env| env.installs.iter())
+    }
+}
+
+fn sdkman_env_type(candidate: &str) -> String {
+    match candidate {
+        "java" => "java".to_string(),
+        other => super::environment::provider_to_env_type(&format!("sdkman-{}", other)),
+    }
+}
+
+fn asdf_env_type(plugin: &str) -> String {
+    match plugin {
+        "nodejs" => "node".to_string(),
+        "golang" => "go".to_string(),
+        other => super::environment::provider_to_env_type(other),
+    }
+}
+
+/// Subdirectories of `dir`, sorted by name. Symlinks are skipped: they are
+/// aliases (`current`) or virtualenvs rather than real installs.
+fn version_dirs(dir: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<(String, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().map(|t| t.is_dir()).unwrap_or(false))
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            (!name.starts_with('.') && name != "current").then(|| (name, entry.path()))
+        })
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+/// Directory holding an install's executables. Go releases unpack into a
+/// `go/` directory, which asdf-golang keeps as is.
+fn bin_dir_of(env_type: &str, install_path: &Path) -> PathBuf {
+    let mut candidates = vec![install_path.join("bin")];
+    if env_type == "go" {
+        candidates.insert(0, install_path.join("go").join("bin"));
+    }
+    candidates
+        .into_iter()
+        .find(|dir| dir.is_dir())
+        .unwrap_or_else(|| install_path.to_path_buf())
+}
+
+fn foreign_install(
+    manager: ForeignManager,
+    env_type: String,
+    version: String,
+    install_path: PathBuf,
+) -> ForeignInstall {
+    let bin_dir = bin_dir_of(&env_type, &install_path);
+    ForeignInstall {
+        manager,
+        env_type,
+        version,
+        install_path,
+        bin_dir,
+        adopted: false,
+    }
+}
+
+/// Installs found under one manager's root directory
+pub fn scan_manager(manager: ForeignManager, root: &Path) -> Vec<ForeignInstall> {
+    match manager {
+        ForeignManager::Nvm => version_dirs(&root.join("versions").join("node"))
+            .into_iter()
+            .map(|(name, path)| {
+                let version = name.strip_prefix('v').unwrap_or(&name).to_string();
+                foreign_install(manager, "node".to_string(), version, path)
+            })
+            .collect(),
+        ForeignManager::Pyenv => version_dirs(&root.join("versions"))
+            .into_iter()
+            .map(|(name, path)| foreign_install(manager, "python".to_string(), name, path))
+            .collect(),
+        ForeignManager::Sdkman => version_dirs(&root.join("candidates"))
+            .into_iter()
+            .flat_map(|(candidate, dir)| {
+                let env_type = sdkman_env_type(&candidate);
+                version_dirs(&dir)
+                    .into_iter()
+                    .map(move |(name, path)| foreign_install(manager, env_type.clone(), name, path))
+            })
+            .collect(),
+        ForeignManager::Asdf => version_dirs(&root.join("installs"))
+            .into_iter()
+            .flat_map(|(plugin, dir)| {
+                let env_type = asdf_env_type(&plugin);
+                version_dirs(&dir)
+                    .into_iter()
+                    .map(move |(name, path)| foreign_install(manager, env_type.clone(), name, path))
+            })
+            .collect(),
+    }
+}
+
+/// Scan every manager root and group the installs by environment type.
+/// Installs present in `adopted` are flagged as such.
+pub fn scan_inventory(
+    roots: &[(ForeignManager, Option<PathBuf>)],
+    adopted: &AdoptedInstalls,
+) -> VersionImportInventory {
+    let mut managers = Vec::new();
+    let mut by_env: BTreeMap<String, Vec<ForeignInstall>> = BTreeMap::new();
+
+    for (manager, root) in roots {
+        let detected = root.as_deref().is_some_and(Path::is_dir);
+        let installs = match root {
+            Some(root) if detected => scan_manager(*manager, root),
+            _ => Vec::new(),
+        };
+        managers.push(ForeignManagerInventory {
+            manager: *manager,
+            root: root.clone(),
+            detected,
+            install_count: installs.len(),
+        });
+        for mut install in installs {
+            install.adopted = adopted.is_adopted(&install);
+            by_env
+                .entry(install.env_type.clone())
+                .or_default()
+                .push(install);
+        }
+    }
+
+    VersionImportInventory {
+        managers,
+        env_types: by_env
+            .into_iter()
+            .map(|(env_type, installs)| ForeignEnvInventory { env_type, installs })
+            .collect(),
+    }
+}
+
+/// Scan the default roots of all supported managers
+pub fn scan_default_inventory(adopted: &AdoptedInstalls) -> VersionImportInventory {
+    let roots: Vec<_> = ForeignManager::all()
+        .into_iter()
+        .map(|manager| (manager, manager.root_dir()))
+        .collect();
+    scan_inventory(&roots, adopted)
+}
+
+/// A foreign install registered for in-place use
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdoptedInstall {
+    pub manager: ForeignManager,
+    pub env_type: String,
+    pub version: String,
+    pub install_path: PathBuf,
+    pub bin_dir: PathBuf,
+    pub adopted_at: String,
+}
+
+impl AdoptedInstall {
+    pub fn from_foreign(install: &ForeignInstall) -> Self {
+        Self {
+            manager: install.manager,
+            env_type: install.env_type.clone(),
+            version: install.version.clone(),
+            install_path: install.install_path.clone(),
+            bin_dir: install.bin_dir.clone(),
+            adopted_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Whether this entry records `install`: the same manager's copy of the
+    /// version, at the same place
+    pub fn is_install(&self, install: &ForeignInstall) -> bool {
+        self.manager == install.manager
+            && self.env_type == install.env_type
+            && self.version == install.version
+            && self.install_path == install.install_path
+    }
+
+    pub fn to_installed_version(&self) -> crate::provider::InstalledVersion {
+        crate::provider::InstalledVersion {
+            version: self.version.clone(),
+            install_path: self.install_path.clone(),
+            size: None,
+            installed_at: Some(self.adopted_at.clone()),
+            is_current: false,
+        }
+    }
+}
+
+/// Registry of adopted installs, persisted as JSON
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AdoptedInstalls {
+    #[serde(default)]
+    pub installs: Vec<AdoptedInstall>,
+}
+
+impl AdoptedInstalls {
+    pub fn default_path() -> Option<PathBuf> {
+        fs::get_cognia_dir().map(|dir| dir.join("state").join("adopted-installs.json"))
+    }
+
+    pub async fn load(path: &Path) -> CogniaResult<Self> {
+        if !fs::exists(path).await {
+            return Ok(Self::default());
+        }
+        let content = fs::read_file_string(path).await?;
+        serde_json::from_str(&content)
+            .map_err(|e| CogniaError::Config(format!("Failed to parse adopted installs: {}", e)))
+    }
+
+    /// Registry at the default path; empty when missing or unreadable
+    pub async fn load_default() -> Self {
+        let Some(path) = Self::default_path() else {
+            return Self::default();
+        };
+        match Self::load(&path).await {
+            Ok(registry) => registry,
+            Err(e) => {
+                log::warn!("Ignoring adopted installs registry: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    pub async fn save(&self, path: &Path) -> CogniaResult<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| {
+            CogniaError::Config(format!("Failed to serialize adopted installs: {}", e))
+        })?;
+        fs::write_file_atomic(path, content.as_bytes()).await?;
+        Ok(())
+    }
+
+    pub async fn save_default(&self) -> CogniaResult<()> {
+        let path = Self::default_path()
+            .ok_or_else(|| CogniaError::Internal("Home directory not found".to_string()))?;
+        self.save(&path).await
+    }
+
+    /// The install adopted for `version`. There is at most one, since all of
+    /// them are linked at the same place in the versions tree.
+    pub fn find(&self, env_type: &str, version: &str) -> Option<&AdoptedInstall> {
+        self.installs
+            .iter()
+            .find(|install| install.env_type == env_type && install.version == version)
+    }
+
+    pub fn is_adopted(&self, install: &ForeignInstall) -> bool {
+        self.installs.iter().any(|entry| entry.is_install(install))
+    }
+
+    pub fn for_env<'a>(&'a self, env_type: &'a str) -> impl Iterator<Item = &'a AdoptedInstall> {
+        self.installs
+            .iter()
+            .filter(move |install| install.env_type == env_type)
+    }
+
+    /// Register an install. Adopting the same install again refreshes its
+    /// entry; adopting another manager's copy of an adopted version fails.
+    pub fn add(&mut self, install: AdoptedInstall) -> CogniaResult<()> {
+        if let Some(existing) = self.find(&install.env_type, &install.version) {
+            if existing.manager != install.manager || existing.install_path != install.install_path
+            {
+                return Err(CogniaError::Conflict(format!(
+                    "{} {} is already adopted from {} at {}",
+                    install.env_type,
+                    install.version,
+                    existing.manager.as_str(),
+                    existing.install_path.display()
+                )));
+            }
+        }
+        self.remove(install.manager, &install.env_type, &install.version);
+        self.installs.push(install);
+        Ok(())
+    }
+
+    pub fn remove(
+        &mut self,
+        manager: ForeignManager,
+        env_type: &str,
+        version: &str,
+    ) -> Option<AdoptedInstall> {
+        let index = self.installs.iter().position(|install| {
+            install.manager == manager && install.env_type == env_type && install.version == version
+        })?;
+        Some(self.installs.remove(index))
+    }
+}
+
+/// `<root>/versions/<env_type>/<version>`, the directory shims exec from
+fn version_link_path(root_dir: &Path, install: &AdoptedInstall) -> PathBuf {
+    root_dir
+        .join("versions")
+        .join(&install.env_type)
+        .join(&install.version)
+}
+
+fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::metadata(path)
+            .map(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+            .unwrap_or(false)
+    }
+    #[cfg(windows)]
+    {
+        path.is_file()
+            && path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| {
+                    matches!(ext.to_ascii_lowercase().as_str(), "exe" | "cmd" | "bat")
+                })
+    }
+}
+
+fn link_dir(target: &Path, link: &Path) -> std::io::Result<()> {
+    if link.symlink_metadata().is_ok() {
+        if link.is_dir() && !link.symlink_metadata()?.file_type().is_symlink() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("{} already exists", link.display()),
+            ));
+        }
+        std::fs::remove_file(link).or_else(|_| std::fs::remove_dir(link))?;
+    }
+    if let Some(parent) = link.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(target, link)
+    }
+    #[cfg(windows)]
+    {
+        std::os::windows::fs::symlink_dir(target, link)
+    }
+}
+
+/// Make an adopted install the global version: link its bin directory
+/// under Cognia's versions tree and point shims at each executable.
+/// Returns the shimmed binary names.
+pub async fn activate_adopted(
+    root_dir: &Path,
+    install: &AdoptedInstall,
+) -> CogniaResult<Vec<String>> {
+    if !install.bin_dir.is_dir() {
+        return Err(CogniaError::Provider(format!(
+            "Adopted {} {} is missing at {}",
+            install.env_type,
+            install.version,
+            install.bin_dir.display()
+        )));
+    }
+    let mut binaries: Vec<(String, PathBuf)> = std::fs::read_dir(&install.bin_dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| is_executable(path))
+        .filter_map(|path| Some((path.file_name()?.to_string_lossy().to_string(), path)))
+        .collect();
+    binaries.sort();
+    if binaries.is_empty() {
+        return Err(CogniaError::Provider(format!(
+            "No executables found in {}",
+            install.bin_dir.display()
+        )));
+    }
+
+    let link = version_link_path(root_dir, install);
+    link_dir(&install.bin_dir, &link)?;
+
+    let mut shims = ShimManager::new(root_dir).await?;
+    for (binary_name, target_path) in &binaries {
+        shims
+            .create_shim(ShimConfig {
+                env_type: install.env_type.clone(),
+                binary_name: binary_name.clone(),
+                version: Some(install.version.clone()),
+                target_path: target_path.clone(),
+                direct: false,
+                version_switch: false,
+            })
+            .await?;
+    }
+
+    // Shims exec through the link, so check each one resolves from there
+    for (binary_name, _) in &binaries {
+        let shimmed = shims
+            .get_shim(binary_name)
+            .is_some_and(|config| config.version.as_deref() == Some(install.version.as_str()));
+        if !shimmed || !link.join(binary_name).is_file() {
+            return Err(CogniaError::Provider(format!(
+                "Shim for {} does not resolve to {}",
+                binary_name,
+                install.bin_dir.display()
+            )));
+        }
+    }
+    Ok(binaries.into_iter().map(|(name, _)| name).collect())
+}
+
+/// Undo [`activate_adopted`]: drop the shims pinned to this install and
+/// its link. The install's own files are left untouched.
+pub async fn deactivate_adopted(root_dir: &Path, install: &AdoptedInstall) -> CogniaResult<()> {
+    let mut shims = ShimManager::new(root_dir).await?;
+    let owned: Vec<String> = shims
+        .list_shims()
+        .into_iter()
+        .filter(|config| {
+            config.env_type == install.env_type
+                && config.version.as_deref() == Some(install.version.as_str())
+        })
+        .map(|config| config.binary_name.clone())
+        .collect();
+    for binary_name in owned {
+        shims.remove_shim(&binary_name).await?;
+    }
+
+    let link = version_link_path(root_dir, install);
+    if link
+        .symlink_metadata()
+        .is_ok_and(|meta| meta.file_type().is_symlink())
+    {
+        std::fs::remove_file(&link).or_else(|_| std::fs::remove_dir(&link))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn mkdirs(root: &Path, paths: &[&str]) {
+        for path in paths {
+            std::fs::create_dir_all(root.join(path)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_scan_manager_layouts() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        mkdirs(
+            root,
+            &[
+                "nvm/versions/node/v20.11.0/bin",
+                "nvm/versions/node/v18.19.0/bin",
+                "pyenv/versions/3.12.1/bin",
+                "sdkman/candidates/java/21.0.2-tem/bin",
+                "sdkman/candidates/kotlin/1.9.22/bin",
+                "asdf/installs/nodejs/22.1.0/bin",
+                "asdf/installs/golang/1.22.0/go/bin",
+            ],
+        );
+
+        let nvm = scan_manager(ForeignManager::Nvm, &root.join("nvm"));
+        let versions: Vec<_> = nvm.iter().map(|i| i.version.as_str()).collect();
+        assert_eq!(versions, vec!["18.19.0", "20.11.0"]);
+        assert!(nvm.iter().all(|i| i.env_type == "node"));
+        assert!(nvm[0].bin_dir.ends_with("bin"));
+
+        let pyenv = scan_manager(ForeignManager::Pyenv, &root.join("pyenv"));
+        assert_eq!(pyenv.len(), 1);
+        assert_eq!(pyenv[0].env_type, "python");
+
+        let sdkman = scan_manager(ForeignManager::Sdkman, &root.join("sdkman"));
+        let envs: Vec<_> = sdkman
+            .iter()
+            .map(|i| (i.env_type.as_str(), i.version.as_str()))
+            .collect();
+        assert_eq!(envs, vec![("java", "21.0.2-tem"), ("kotlin", "1.9.22")]);
+
+        let asdf = scan_manager(ForeignManager::Asdf, &root.join("asdf"));
+        let envs: Vec<_> = asdf.iter().map(|i| i.env_type.as_str()).collect();
+        assert_eq!(envs, vec!["go", "node"]);
+        // asdf-golang keeps the release's go/ directory
+        assert!(asdf[0].bin_dir.ends_with("go/bin"));
+        assert!(asdf[1].bin_dir.ends_with("22.1.0/bin"));
+    }
+
+    #[test]
+    fn test_bin_dir_falls_back_to_install_path() {
+        let dir = TempDir::new().unwrap();
+        mkdirs(dir.path(), &["sdkman/candidates/maven/3.9.6"]);
+
+        let installs = scan_manager(ForeignManager::Sdkman, &dir.path().join("sdkman"));
+        assert_eq!(installs[0].bin_dir, installs[0].install_path);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scan_skips_symlinked_aliases() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        mkdirs(root, &["candidates/java/21.0.2-tem"]);
+        std::os::unix::fs::symlink(
+            root.join("candidates/java/21.0.2-tem"),
+            root.join("candidates/java/current"),
+        )
+        .unwrap();
+
+        let installs = scan_manager(ForeignManager::Sdkman, root);
+        assert_eq!(installs.len(), 1);
+        assert_eq!(installs[0].version, "21.0.2-tem");
+    }
+
+    #[test]
+    fn test_scan_inventory_groups_and_flags_adopted() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        mkdirs(
+            root,
+            &[
+                "nvm/versions/node/v20.11.0",
+                "asdf/installs/nodejs/22.1.0",
+                "asdf/installs/python/3.11.8",
+            ],
+        );
+
+        let nvm = scan_manager(ForeignManager::Nvm, &root.join("nvm"));
+        let mut adopted = AdoptedInstalls::default();
+        adopted.add(AdoptedInstall::from_foreign(&nvm[0])).unwrap();
+
+        let inventory = scan_inventory(
+            &[
+                (ForeignManager::Nvm, Some(root.join("nvm"))),
+                (ForeignManager::Asdf, Some(root.join("asdf"))),
+                (ForeignManager::Pyenv, Some(root.join("missing"))),
+            ],
+            &adopted,
+        );
+
+        assert!(!inventory.managers[2].detected);
+        assert_eq!(inventory.managers[1].install_count, 2);
+        let env_types: Vec<_> = inventory
+            .env_types
+            .iter()
+            .map(|env| env.env_type.as_str())
+            .collect();
+        assert_eq!(env_types, vec!["node", "python"]);
+        let node = &inventory.env_types[0].installs;
+        assert_eq!(node.len(), 2);
+        assert!(node.iter().any(|i| i.version == "20.11.0" && i.adopted));
+        assert!(node.iter().any(|i| i.version == "22.1.0" && !i.adopted));
+    }
+
+    #[tokio::test]
+    async fn test_adopted_registry_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("state").join("adopted-installs.json");
+        mkdirs(dir.path(), &["pyenv/versions/3.12.1"]);
+        let installs = scan_manager(ForeignManager::Pyenv, &dir.path().join("pyenv"));
+
+        let mut registry = AdoptedInstalls::default();
+        registry
+            .add(AdoptedInstall::from_foreign(&installs[0]))
+            .unwrap();
+        registry
+            .add(AdoptedInstall::from_foreign(&installs[0]))
+            .unwrap();
+        registry.save(&path).await.unwrap();
+
+        let mut loaded = AdoptedInstalls::load(&path).await.unwrap();
+        assert_eq!(loaded.installs.len(), 1);
+        assert_eq!(loaded.for_env("python").count(), 1);
+        assert!(loaded.is_adopted(&installs[0]));
+        assert!(loaded
+            .remove(ForeignManager::Asdf, "python", "3.12.1")
+            .is_none());
+        assert!(loaded
+            .remove(ForeignManager::Pyenv, "python", "3.12.1")
+            .is_some());
+        assert!(loaded.find("python", "3.12.1").is_none());
+    }
+
+    #[test]
+    fn test_adopting_same_version_from_another_manager_conflicts() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        mkdirs(
+            root,
+            &["nvm/versions/node/v20.11.0", "asdf/installs/nodejs/20.11.0"],
+        );
+        let nvm = scan_manager(ForeignManager::Nvm, &root.join("nvm"));
+        let asdf = scan_manager(ForeignManager::Asdf, &root.join("asdf"));
+
+        let mut adopted = AdoptedInstalls::default();
+        adopted.add(AdoptedInstall::from_foreign(&nvm[0])).unwrap();
+        let err = adopted
+            .add(AdoptedInstall::from_foreign(&asdf[0]))
+            .unwrap_err();
+        assert!(matches!(err, CogniaError::Conflict(_)));
+
+        assert!(adopted.is_adopted(&nvm[0]));
+        assert!(!adopted.is_adopted(&asdf[0]));
+        assert_eq!(
+            adopted.find("node", "20.11.0").unwrap().manager,
+            ForeignManager::Nvm
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_activate_and_deactivate_adopted() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new().unwrap();
+        let cognia_root = dir.path().join("cognia");
+        mkdirs(dir.path(), &["nvm/versions/node/v20.11.0/bin"]);
+        let bin = dir.path().join("nvm/versions/node/v20.11.0/bin/node");
+        std::fs::write(&bin, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&bin, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let installs = scan_manager(ForeignManager::Nvm, &dir.path().join("nvm"));
+        let adopted = AdoptedInstall::from_foreign(&installs[0]);
+
+        let shimmed = activate_adopted(&cognia_root, &adopted).await.unwrap();
+        assert_eq!(shimmed, vec!["node".to_string()]);
+        let link = cognia_root.join("versions/node/20.11.0");
+        assert!(link.join("node").is_file());
+
+        let shims = ShimManager::new(&cognia_root).await.unwrap();
+        assert_eq!(
+            shims.get_shim("node").unwrap().version.as_deref(),
+            Some("20.11.0")
+        );
+
+        deactivate_adopted(&cognia_root, &adopted).await.unwrap();
+        assert!(link.symlink_metadata().is_err());
+        // The foreign install itself is never touched
+        assert!(bin.is_file());
+        let shims = ShimManager::new(&cognia_root).await.unwrap();
+        assert!(shims.list_shims().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_activate_without_executables_fails() {
+        let dir = TempDir::new().unwrap();
+        let cognia_root = dir.path().join("cognia");
+        mkdirs(dir.path(), &["pyenv/versions/3.12.1/bin"]);
+
+        let installs = scan_manager(ForeignManager::Pyenv, &dir.path().join("pyenv"));
+        let adopted = AdoptedInstall::from_foreign(&installs[0]);
+
+        assert!(activate_adopted(&cognia_root, &adopted).await.is_err());
+        assert!(cognia_root
+            .join("versions/python/3.12.1")
+            .symlink_metadata()
+            .is_err());
+    }
+}
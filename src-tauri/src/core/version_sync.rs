@@ -0,0 +1,302 @@
+//! Incremental sync of provider version lists.
+//!
+//! Each provider's available versions are kept as a [`VersionIndex`] together
+//! with the validators and versions needed to ask upstream for only what
+//! changed. Versions that disappear upstream are marked yanked and dropped
+//! once they have been gone for [`YANKED_RETENTION_DAYS`].
+
+use crate::provider::{VersionFetch, VersionInfo, VersionSyncCursor};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Deltas cannot see upstream deletions, so a full list is refetched this often
+pub const FULL_RESYNC_INTERVAL_DAYS: i64 = 7;
+/// How long a version removed upstream stays listed as yanked
+pub const YANKED_RETENTION_DAYS: i64 = 30;
+
+/// Last synced version list of one provider.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionIndex {
+    pub provider_id: String,
+    pub versions: Vec<VersionInfo>,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub last_synced: DateTime<Utc>,
+    pub last_full_sync: DateTime<Utc>,
+    /// Versions no longer listed upstream, with when they were first missed
+    #[serde(default)]
+    pub missing_since: HashMap<String, DateTime<Utc>>,
+}
+
+impl VersionIndex {
+    /// Cursor for the next incremental fetch.
+    pub fn cursor(&self) -> VersionSyncCursor {
+        VersionSyncCursor {
+            etag: self.etag.clone(),
+            last_modified: self.last_modified.clone(),
+            known_versions: self
+                .versions
+                .iter()
+                .filter(|info| !self.missing_since.contains_key(&info.version))
+                .map(|info| info.version.clone())
+                .collect(),
+        }
+    }
+
+    pub fn needs_full_sync(&self, now: DateTime<Utc>) -> bool {
+        now - self.last_full_sync >= Duration::days(FULL_RESYNC_INTERVAL_DAYS)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionSyncMode {
+    Unchanged,
+    Full,
+    Delta,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionSyncStats {
+    pub mode: VersionSyncMode,
+    /// Versions not seen before this sync
+    pub added: usize,
+    /// Versions newly marked yanked because upstream stopped listing them
+    pub yanked: usize,
+    /// Yanked versions dropped after the retention period
+    pub pruned: usize,
+}
+
+/// Available versions with the time they were last synced from upstream.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvailableVersions {
+    pub versions: Vec<VersionInfo>,
+    pub last_synced: Option<DateTime<Utc>>,
+}
+
+impl From<&VersionIndex> for AvailableVersions {
+    fn from(index: &VersionIndex) -> Self {
+        Self {
+            versions: index.versions.clone(),
+            last_synced: Some(index.last_synced),
+        }
+    }
+}
+
+/// Result of an explicit refresh.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionSyncResult {
+    pub env_type: String,
+    pub provider_id: String,
+    pub versions: Vec<VersionInfo>,
+    pub last_synced: DateTime<Utc>,
+    pub stats: VersionSyncStats,
+}
+
+/// Merge `fetch` into `previous`, returning the new index.
+pub fn apply_fetch(
+    previous: Option<VersionIndex>,
+    provider_id: &str,
+    fetch: VersionFetch,
+    now: DateTime<Utc>,
+) -> (VersionIndex, VersionSyncStats) {
+    let mut index = previous.unwrap_or_else(|| VersionIndex {
+        provider_id: provider_id.to_string(),
+        versions: Vec::new(),
+        etag: None,
+        last_modified: None,
+        last_synced: now,
+        last_full_sync: now,
+        missing_since: HashMap::new(),
+    });
+    let known: HashSet<String> = index
+        .versions
+        .iter()
+        .map(|info| info.version.clone())
+        .collect();
+    let mut stats = VersionSyncStats {
+        mode: VersionSyncMode::Unchanged,
+        added: 0,
+        yanked: 0,
+        pruned: 0,
+    };
+
+    match fetch {
+        VersionFetch::Unchanged => {}
+        VersionFetch::Full {
+            versions,
+            etag,
+            last_modified,
+        } => {
+            stats.mode = VersionSyncMode::Full;
+            let listed: HashSet<String> =
+                versions.iter().map(|info| info.version.clone()).collect();
+            stats.added = listed.difference(&known).count();
+
+            let mut merged = versions;
+            let mut missing_since = HashMap::new();
+            for mut info in std::mem::take(&mut index.versions) {
+                if listed.contains(&info.version) {
+                    continue;
+                }
+                let since = match index.missing_since.get(&info.version) {
+                    Some(since) => *since,
+                    None => {
+                        stats.yanked += 1;
+                        now
+                    }
+                };
+                info.yanked = true;
+                missing_since.insert(info.version.clone(), since);
+                merged.push(info);
+            }
+            index.versions = merged;
+            index.missing_since = missing_since;
+            index.etag = etag;
+            index.last_modified = last_modified;
+            index.last_full_sync = now;
+        }
+        VersionFetch::Delta {
+            versions,
+            etag,
+            last_modified,
+        } => {
+            stats.mode = VersionSyncMode::Delta;
+            let mut fresh = Vec::new();
+            for info in versions {
+                index.missing_since.remove(&info.version);
+                match index
+                    .versions
+                    .iter_mut()
+                    .find(|existing| existing.version == info.version)
+                {
+                    Some(existing) => *existing = info,
+                    None => fresh.push(info),
+                }
+            }
+            stats.added = fresh.len();
+            fresh.append(&mut index.versions);
+            index.versions = fresh;
+            index.etag = etag.or(index.etag);
+            index.last_modified = last_modified.or(index.last_modified);
+        }
+    }
+
+    let retention = Duration::days(YANKED_RETENTION_DAYS);
+    let expired: HashSet<String> = index
+        .missing_since
+        .iter()
+        .filter(|(_, since)| now - **since >= retention)
+        .map(|(version, _)| version.clone())
+        .collect();
+    if !expired.is_empty() {
+        index
+            .versions
+            .retain(|info| !expired.contains(&info.version));
+        index
+            .missing_since
+            .retain(|version, _| !expired.contains(version));
+        stats.pruned = expired.len();
+    }
+
+    index.provider_id = provider_id.to_string();
+    index.last_synced = now;
+    (index, stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(version: &str) -> VersionInfo {
+        VersionInfo {
+            version: version.to_string(),
+            release_date: None,
+            deprecated: false,
+            yanked: false,
+        }
+    }
+
+    fn full(versions: &[&str]) -> VersionFetch {
+        VersionFetch::Full {
+            versions: versions.iter().map(|v| info(v)).collect(),
+            etag: Some("\"v1\"".to_string()),
+            last_modified: None,
+        }
+    }
+
+    fn version_names(index: &VersionIndex) -> Vec<&str> {
+        index.versions.iter().map(|v| v.version.as_str()).collect()
+    }
+
+    #[test]
+    fn test_delta_prepends_new_versions() {
+        let now = Utc::now();
+        let (index, _) = apply_fetch(None, "deno", full(&["1.2.0", "1.1.0"]), now);
+        assert_eq!(index.cursor().known_versions.len(), 2);
+
+        let (index, stats) = apply_fetch(
+            Some(index),
+            "deno",
+            VersionFetch::Delta {
+                versions: vec![info("1.3.0")],
+                etag: None,
+                last_modified: None,
+            },
+            now,
+        );
+        assert_eq!(stats.mode, VersionSyncMode::Delta);
+        assert_eq!(stats.added, 1);
+        assert_eq!(version_names(&index), vec!["1.3.0", "1.2.0", "1.1.0"]);
+        // A delta without validators keeps the previous ones
+        assert_eq!(index.etag.as_deref(), Some("\"v1\""));
+    }
+
+    #[test]
+    fn test_full_sync_marks_removed_versions_yanked_then_prunes() {
+        let start = Utc::now();
+        let (index, _) = apply_fetch(None, "zig", full(&["0.12.0", "0.11.0"]), start);
+
+        let (index, stats) = apply_fetch(Some(index), "zig", full(&["0.12.0"]), start);
+        assert_eq!(stats.yanked, 1);
+        assert_eq!(version_names(&index), vec!["0.12.0", "0.11.0"]);
+        assert!(index.versions[1].yanked);
+        assert!(!index.cursor().known_versions.contains("0.11.0"));
+
+        // Still missing later: not counted again, kept until retention runs out
+        let later = start + Duration::days(1);
+        let (index, stats) = apply_fetch(Some(index), "zig", full(&["0.12.0"]), later);
+        assert_eq!(stats.yanked, 0);
+        assert_eq!(index.versions.len(), 2);
+
+        let expired = start + Duration::days(YANKED_RETENTION_DAYS);
+        let (index, stats) = apply_fetch(Some(index), "zig", VersionFetch::Unchanged, expired);
+        assert_eq!(stats.pruned, 1);
+        assert_eq!(version_names(&index), vec!["0.12.0"]);
+        assert!(index.missing_since.is_empty());
+    }
+
+    #[test]
+    fn test_reappearing_version_is_restored() {
+        let now = Utc::now();
+        let (index, _) = apply_fetch(None, "zig", full(&["0.12.0", "0.11.0"]), now);
+        let (index, _) = apply_fetch(Some(index), "zig", full(&["0.12.0"]), now);
+        let (index, stats) = apply_fetch(Some(index), "zig", full(&["0.12.0", "0.11.0"]), now);
+        assert_eq!(stats.added, 0);
+        assert!(index.versions.iter().all(|v| !v.yanked));
+        assert!(index.missing_since.is_empty());
+    }
+
+    #[test]
+    fn test_needs_full_sync_after_interval() {
+        let now = Utc::now();
+        let (index, _) = apply_fetch(None, "deno", full(&["1.0.0"]), now);
+        assert!(!index.needs_full_sync(now));
+        assert!(index.needs_full_sync(now + Duration::days(FULL_RESYNC_INTERVAL_DAYS)));
+    }
+}
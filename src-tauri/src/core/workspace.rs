@@ -0,0 +1,814 @@
+//! Project workspaces: named groups of git repositories found under one or
+//! more root directories.
+//!
+//! A scan records each repo's project env pins together with a fingerprint
+//! of its git HEAD and manifest mtimes, so later scans only re-run
+//! detection for repos that changed.
+
+use crate::core::env_disk_usage::version_matches_pin;
+use crate::core::DetectedEnvironment;
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::fs;
+use crate::provider::git::GitAheadBehind;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+/// How deep below a root directory repos are searched for
+pub const MAX_DISCOVERY_DEPTH: usize = 6;
+
+/// Directories never descended into while discovering repos
+const SKIPPED_DIR_NAMES: &[&str] = &[".git", "node_modules", "target", ".venv", "__pycache__"];
+
+/// A named group of repositories
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Workspace {
+    pub id: String,
+    pub name: String,
+    pub root_paths: Vec<String>,
+    /// Globs matched against paths relative to a root (`archive/**`) or
+    /// against a single directory name (`legacy-*`)
+    #[serde(default)]
+    pub exclude_globs: Vec<String>,
+    /// Repos found by the last scan
+    #[serde(default)]
+    pub repos: Vec<WorkspaceRepo>,
+    #[serde(default)]
+    pub last_scanned_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl Workspace {
+    pub fn new(name: impl Into<String>, root_paths: Vec<String>) -> Self {
+        let now = chrono::Utc::now().to_rfc3339();
+        Self {
+            id: Uuid::new_v4().to_string(),
+            name: name.into(),
+            root_paths,
+            exclude_globs: Vec::new(),
+            repos: Vec::new(),
+            last_scanned_at: None,
+            created_at: now.clone(),
+            updated_at: now,
+        }
+    }
+
+    pub fn with_exclude_globs(mut self, exclude_globs: Vec<String>) -> Self {
+        self.exclude_globs = exclude_globs;
+        self
+    }
+}
+
+/// A repository found by a workspace scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceRepo {
+    pub path: String,
+    pub name: String,
+    /// Checked-out branch, `None` for a detached HEAD
+    pub branch: Option<String>,
+    pub pins: Vec<DetectedEnvironment>,
+    #[serde(default)]
+    pub fingerprint: RepoFingerprint,
+}
+
+/// What a repo looked like when its pins were detected
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepoFingerprint {
+    /// Symbolic ref and commit HEAD points at
+    pub head: Option<String>,
+    /// Modification time in milliseconds of every manifest present,
+    /// keyed by path relative to the repo
+    pub manifest_mtimes: BTreeMap<String, u64>,
+}
+
+/// Outcome of [`scan_workspace`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceScanResult {
+    pub workspace_id: String,
+    pub repos_found: usize,
+    /// Repos whose pins were detected again
+    pub rescanned: usize,
+    /// Repos whose HEAD and manifests had not changed
+    pub unchanged: usize,
+    /// Repos from the previous scan that are gone or now excluded
+    pub removed: usize,
+}
+
+/// A toolchain version missing from one or more repos
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceToolchain {
+    pub env_type: String,
+    pub version: String,
+    /// Repos that pin this version
+    pub repos: Vec<String>,
+}
+
+/// Per-repo state reported by `workspace_status`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceRepoStatus {
+    pub path: String,
+    pub name: String,
+    pub branch: Option<String>,
+    pub pins: Vec<DetectedEnvironment>,
+    /// Pins no installed version satisfies
+    pub missing: Vec<DetectedEnvironment>,
+    /// Relative to the branch's upstream; `None` when detached or on error
+    pub ahead_behind: Option<GitAheadBehind>,
+    pub git_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceStatus {
+    pub workspace_id: String,
+    pub name: String,
+    pub last_scanned_at: Option<String>,
+    pub repos: Vec<WorkspaceRepoStatus>,
+    /// Deduplicated installs needed across all repos
+    pub missing_toolchains: Vec<WorkspaceToolchain>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceToolchainError {
+    pub env_type: String,
+    pub version: String,
+    pub error: String,
+}
+
+/// Outcome of installing a workspace's missing toolchains
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceApplyResult {
+    pub workspace_id: String,
+    pub installed: Vec<WorkspaceToolchain>,
+    pub failed: Vec<WorkspaceToolchainError>,
+    /// Toolchains not attempted because the operation was cancelled
+    pub skipped: Vec<WorkspaceToolchain>,
+}
+
+/// Manager for persisted workspaces
+pub struct WorkspaceManager {
+    workspaces: Vec<Workspace>,
+    config_path: PathBuf,
+}
+
+impl WorkspaceManager {
+    pub fn new(config_dir: &Path) -> Self {
+        Self {
+            workspaces: Vec::new(),
+            config_path: config_dir.join("workspaces.json"),
+        }
+    }
+
+    /// Load workspaces from the config file
+    pub async fn load(&mut self) -> CogniaResult<()> {
+        if self.config_path.exists() {
+            let content = fs::read_file_string(&self.config_path).await?;
+            self.workspaces = serde_json::from_str(&content)
+                .map_err(|e| CogniaError::Parse(format!("Failed to parse workspaces: {}", e)))?;
+        }
+        Ok(())
+    }
+
+    /// Save workspaces to the config file
+    pub async fn save(&self) -> CogniaResult<()> {
+        if let Some(parent) = self.config_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let content = serde_json::to_string_pretty(&self.workspaces)
+            .map_err(|e| CogniaError::Parse(format!("Failed to serialize workspaces: {}", e)))?;
+        fs::write_file_string(&self.config_path, &content).await?;
+        Ok(())
+    }
+
+    pub fn list(&self) -> &[Workspace] {
+        &self.workspaces
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Workspace> {
+        self.workspaces.iter().find(|w| w.id == id)
+    }
+
+    /// Add a workspace. Names are unique and at least one root is required.
+    pub fn create(&mut self, mut workspace: Workspace) -> CogniaResult<Workspace> {
+        let name = workspace.name.trim().to_string();
+        if name.is_empty() {
+            return Err(CogniaError::Config("Workspace name is required".into()));
+        }
+        if workspace.root_paths.iter().all(|p| p.trim().is_empty()) {
+            return Err(CogniaError::Config(
+                "Workspace needs at least one root path".into(),
+            ));
+        }
+        if self.workspaces.iter().any(|w| w.name == name) {
+            return Err(CogniaError::Config(format!(
+                "Workspace '{}' already exists",
+                name
+            )));
+        }
+
+        workspace.name = name;
+        workspace.root_paths.retain(|p| !p.trim().is_empty());
+        workspace.exclude_globs.retain(|g| !g.trim().is_empty());
+        self.workspaces.push(workspace.clone());
+        Ok(workspace)
+    }
+
+    pub fn delete(&mut self, id: &str) -> CogniaResult<()> {
+        let before = self.workspaces.len();
+        self.workspaces.retain(|w| w.id != id);
+        if self.workspaces.len() == before {
+            return Err(CogniaError::Config(format!("Workspace '{}' not found", id)));
+        }
+        Ok(())
+    }
+
+    /// Store the repos found by a scan
+    pub fn set_repos(&mut self, id: &str, repos: Vec<WorkspaceRepo>) -> CogniaResult<()> {
+        let workspace = self
+            .workspaces
+            .iter_mut()
+            .find(|w| w.id == id)
+            .ok_or_else(|| CogniaError::Config(format!("Workspace '{}' not found", id)))?;
+        let now = chrono::Utc::now().to_rfc3339();
+        workspace.repos = repos;
+        workspace.last_scanned_at = Some(now.clone());
+        workspace.updated_at = now;
+        Ok(())
+    }
+}
+
+/// Turn a glob into a regex: `**` crosses directories, `*` and `?` do not
+pub(crate) fn glob_to_regex(glob: &str) -> Option<Regex> {
+    let mut pattern = String::from("^");
+    let mut chars = glob.trim().chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    pattern.push_str("(?:.*/)?");
+                } else {
+                    pattern.push_str(".*");
+                }
+            }
+            '*' => pattern.push_str("[^/]*"),
+            '?' => pattern.push_str("[^/]"),
+            '\\' => pattern.push('/'),
+            other => pattern.push_str(&regex::escape(&other.to_string())),
+        }
+    }
+    pattern.push('$');
+    Regex::new(&pattern).ok()
+}
+
+/// Compiled exclusion globs
+struct ExcludeSet(Vec<Regex>);
+
+impl ExcludeSet {
+    fn new(globs: &[String]) -> Self {
+        Self(globs.iter().filter_map(|g| glob_to_regex(g)).collect())
+    }
+
+    /// `relative` uses `/` separators; `name` is the last component
+    fn is_excluded(&self, relative: &str, name: &str) -> bool {
+        self.0
+            .iter()
+            .any(|re| re.is_match(relative) || re.is_match(name))
+    }
+}
+
+fn relative_key(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Git repositories under `roots`, sorted and without duplicates. Repos
+/// nested inside another repo are not reported.
+pub fn discover_repos(roots: &[PathBuf], exclude_globs: &[String]) -> Vec<PathBuf> {
+    let excludes = ExcludeSet::new(exclude_globs);
+    let mut repos = BTreeSet::new();
+
+    for root in roots {
+        let mut walker = WalkDir::new(root)
+            .max_depth(MAX_DISCOVERY_DEPTH)
+            .follow_links(false)
+            .into_iter();
+        while let Some(entry) = walker.next() {
+            let Ok(entry) = entry else {
+                continue;
+            };
+            if !entry.file_type().is_dir() {
+                continue;
+            }
+            let path = entry.path();
+            if entry.depth() > 0 {
+                let name = entry.file_name().to_string_lossy();
+                if SKIPPED_DIR_NAMES.contains(&name.as_ref())
+                    || excludes.is_excluded(&relative_key(root, path), &name)
+                {
+                    walker.skip_current_dir();
+                    continue;
+                }
+            }
+            if path.join(".git").exists() {
+                repos.insert(path.to_path_buf());
+                walker.skip_current_dir();
+            }
+        }
+    }
+
+    repos.into_iter().collect()
+}
+
+/// The repo's git directory, following the `gitdir:` file used by
+/// worktrees and submodules
+fn git_dir(repo: &Path) -> Option<PathBuf> {
+    let dot_git = repo.join(".git");
+    if dot_git.is_dir() {
+        return Some(dot_git);
+    }
+    let content = std::fs::read_to_string(&dot_git).ok()?;
+    let target = PathBuf::from(content.trim().strip_prefix("gitdir:")?.trim());
+    Some(if target.is_absolute() {
+        target
+    } else {
+        repo.join(target)
+    })
+}
+
+/// Commit a ref points at, from a loose ref file or `packed-refs`
+fn resolve_ref(git_dir: &Path, reference: &str) -> Option<String> {
+    if let Ok(commit) = std::fs::read_to_string(git_dir.join(reference)) {
+        return Some(commit.trim().to_string());
+    }
+    let packed = std::fs::read_to_string(git_dir.join("packed-refs")).ok()?;
+    packed.lines().find_map(|line| {
+        let (commit, name) = line.split_once(' ')?;
+        (name.trim() == reference).then(|| commit.to_string())
+    })
+}
+
+/// `refs/heads/<branch>@<commit>` for a branch, the bare commit when detached
+fn read_head(repo: &Path) -> Option<String> {
+    let git_dir = git_dir(repo)?;
+    let head = std::fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let head = head.trim();
+    match head.strip_prefix("ref:") {
+        Some(reference) => {
+            let reference = reference.trim();
+            let commit = resolve_ref(&git_dir, reference).unwrap_or_default();
+            Some(format!("{}@{}", reference, commit))
+        }
+        None => Some(head.to_string()),
+    }
+}
+
+/// Branch named by a fingerprint's HEAD
+pub fn head_branch(head: &str) -> Option<String> {
+    let reference = head.split_once('@').map_or(head, |(r, _)| r);
+    reference
+        .strip_prefix("refs/heads/")
+        .map(|branch| branch.to_string())
+}
+
+/// File a detection source reads, e.g. `package.json` for
+/// `package.json (engines.node)`
+fn source_file(source: &str) -> &str {
+    source.split(" (").next().unwrap_or(source).trim()
+}
+
+/// Manifest files any of `sources` may read, relative to a repo
+pub fn manifest_files(sources: &[(String, Vec<String>)]) -> BTreeSet<String> {
+    sources
+        .iter()
+        .flat_map(|(_, labels)| labels.iter())
+        .map(|label| source_file(label).to_string())
+        .filter(|file| !file.is_empty())
+        .collect()
+}
+
+pub fn repo_fingerprint(repo: &Path, manifests: &BTreeSet<String>) -> RepoFingerprint {
+    let manifest_mtimes = manifests
+        .iter()
+        .filter_map(|file| {
+            let modified = std::fs::metadata(repo.join(file)).ok()?.modified().ok()?;
+            let millis = modified.duration_since(UNIX_EPOCH).ok()?.as_millis();
+            Some((file.clone(), u64::try_from(millis).unwrap_or(u64::MAX)))
+        })
+        .collect();
+    RepoFingerprint {
+        head: read_head(repo),
+        manifest_mtimes,
+    }
+}
+
+/// Project pins (no global fallback) for every env type in `sources`
+async fn detect_repo_pins(
+    repo: &Path,
+    sources: &[(String, Vec<String>)],
+) -> Vec<DetectedEnvironment> {
+    let detections = sources.iter().map(|(env_type, labels)| {
+        super::project_env_detect::detect_env_version(env_type, repo, labels)
+    });
+    futures::future::join_all(detections)
+        .await
+        .into_iter()
+        .filter_map(|result| result.ok().flatten())
+        .collect()
+}
+
+/// Discover the workspace's repos and detect their pins. Repos whose
+/// fingerprint matches `workspace.repos` keep their previous pins unless
+/// `full` is set. Pins inherited from above a repo's root are only
+/// refreshed by a full scan.
+pub async fn scan_workspace(
+    workspace: &Workspace,
+    sources: &[(String, Vec<String>)],
+    full: bool,
+) -> CogniaResult<(Vec<WorkspaceRepo>, WorkspaceScanResult)> {
+    let roots: Vec<PathBuf> = workspace.root_paths.iter().map(PathBuf::from).collect();
+    let exclude_globs = workspace.exclude_globs.clone();
+    let manifests = manifest_files(sources);
+
+    let found = tokio::task::spawn_blocking(move || {
+        discover_repos(&roots, &exclude_globs)
+            .into_iter()
+            .map(|repo| {
+                let fingerprint = repo_fingerprint(&repo, &manifests);
+                (repo, fingerprint)
+            })
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| CogniaError::Internal(format!("Workspace scan failed: {}", e)))?;
+
+    let previous: HashMap<&str, &WorkspaceRepo> = workspace
+        .repos
+        .iter()
+        .map(|repo| (repo.path.as_str(), repo))
+        .collect();
+
+    let mut repos = Vec::with_capacity(found.len());
+    let mut rescanned = 0;
+    for (path, fingerprint) in found {
+        let key = path.to_string_lossy().to_string();
+        let unchanged = previous
+            .get(key.as_str())
+            .filter(|repo| !full && repo.fingerprint == fingerprint);
+        let pins = match unchanged {
+            Some(repo) => repo.pins.clone(),
+            None => {
+                rescanned += 1;
+                detect_repo_pins(&path, sources).await
+            }
+        };
+        repos.push(WorkspaceRepo {
+            name: path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| key.clone()),
+            branch: fingerprint.head.as_deref().and_then(head_branch),
+            path: key,
+            pins,
+            fingerprint,
+        });
+    }
+
+    let removed = workspace
+        .repos
+        .iter()
+        .filter(|old| !repos.iter().any(|repo| repo.path == old.path))
+        .count();
+    let result = WorkspaceScanResult {
+        workspace_id: workspace.id.clone(),
+        repos_found: repos.len(),
+        rescanned,
+        unchanged: repos.len() - rescanned,
+        removed,
+    };
+    Ok((repos, result))
+}
+
+/// Pins of `repo` that no installed version satisfies. `installed` maps a
+/// logical env type to its installed versions.
+pub fn missing_pins(
+    repo: &WorkspaceRepo,
+    installed: &HashMap<String, Vec<String>>,
+) -> Vec<DetectedEnvironment> {
+    repo.pins
+        .iter()
+        .filter(|pin| {
+            !installed.get(&pin.env_type).is_some_and(|versions| {
+                versions
+                    .iter()
+                    .any(|version| version_matches_pin(version, &pin.version))
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+/// Toolchains to install so every repo's pins are satisfied. Identical
+/// pins are installed once, and a partial pin (`18`) is dropped when a
+/// more specific missing pin (`18.19.0`) already satisfies it.
+pub fn plan_missing_toolchains(
+    repos: &[WorkspaceRepo],
+    installed: &HashMap<String, Vec<String>>,
+) -> Vec<WorkspaceToolchain> {
+    let mut wanted: BTreeMap<(String, String), Vec<String>> = BTreeMap::new();
+    for repo in repos {
+        for pin in missing_pins(repo, installed) {
+            let version = pin.version.trim().trim_start_matches('v').to_string();
+            let users = wanted.entry((pin.env_type, version)).or_default();
+            if !users.contains(&repo.path) {
+                users.push(repo.path.clone());
+            }
+        }
+    }
+
+    let mut plan: Vec<WorkspaceToolchain> = Vec::new();
+    for ((env_type, version), repos) in &wanted {
+        let covered = wanted.keys().any(|(other_type, other)| {
+            other_type == env_type && other != version && version_matches_pin(other, version)
+        });
+        if !covered {
+            plan.push(WorkspaceToolchain {
+                env_type: env_type.clone(),
+                version: version.clone(),
+                repos: repos.clone(),
+            });
+        }
+    }
+
+    // Repos whose partial pin was folded into a more specific version
+    for ((env_type, version), repos) in &wanted {
+        if let Some(target) = plan.iter_mut().find(|t| {
+            &t.env_type == env_type
+                && &t.version != version
+                && version_matches_pin(&t.version, version)
+        }) {
+            for repo in repos {
+                if !target.repos.contains(repo) {
+                    target.repos.push(repo.clone());
+                }
+            }
+        }
+    }
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn init_repo(path: &Path, branch: &str, commit: &str) {
+        std::fs::create_dir_all(path.join(".git/refs/heads")).unwrap();
+        std::fs::write(
+            path.join(".git/HEAD"),
+            format!("ref: refs/heads/{}\n", branch),
+        )
+        .unwrap();
+        std::fs::write(
+            path.join(".git/refs/heads").join(branch),
+            format!("{}\n", commit),
+        )
+        .unwrap();
+    }
+
+    fn pin(env_type: &str, version: &str) -> DetectedEnvironment {
+        DetectedEnvironment {
+            env_type: env_type.to_string(),
+            version: version.to_string(),
+            source: ".tool-versions".to_string(),
+            source_path: None,
+            source_type: "local".to_string(),
+            resolved_via: None,
+            resolved_version: None,
+            suggested_version: None,
+        }
+    }
+
+    fn repo(path: &str, pins: Vec<DetectedEnvironment>) -> WorkspaceRepo {
+        WorkspaceRepo {
+            path: path.to_string(),
+            name: path.to_string(),
+            branch: None,
+            pins,
+            fingerprint: RepoFingerprint::default(),
+        }
+    }
+
+    fn node_sources() -> Vec<(String, Vec<String>)> {
+        vec![(
+            "node".to_string(),
+            vec![
+                ".nvmrc".to_string(),
+                "package.json (engines.node)".to_string(),
+            ],
+        )]
+    }
+
+    #[test]
+    fn test_glob_to_regex() {
+        let re = glob_to_regex("archive/**").unwrap();
+        assert!(re.is_match("archive/old/app"));
+        assert!(!re.is_match("src/archive"));
+
+        let re = glob_to_regex("legacy-*").unwrap();
+        assert!(re.is_match("legacy-api"));
+        assert!(!re.is_match("legacy-api/sub"));
+
+        let re = glob_to_regex("**/fixtures").unwrap();
+        assert!(re.is_match("fixtures"));
+        assert!(re.is_match("a/b/fixtures"));
+
+        let re = glob_to_regex("v?.x").unwrap();
+        assert!(re.is_match("v1.x"));
+        assert!(!re.is_match("v1ax"));
+    }
+
+    #[test]
+    fn test_discover_repos_skips_excluded_and_nested() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        init_repo(&root.join("app"), "main", "a1");
+        init_repo(&root.join("app/vendor/lib"), "main", "b2");
+        init_repo(&root.join("group/service"), "main", "c3");
+        init_repo(&root.join("archive/old"), "main", "d4");
+        init_repo(&root.join("legacy-tool"), "main", "e5");
+        init_repo(&root.join("web/node_modules/pkg"), "main", "f6");
+
+        let repos = discover_repos(
+            &[root.to_path_buf()],
+            &["archive/**".to_string(), "legacy-*".to_string()],
+        );
+        assert_eq!(repos, vec![root.join("app"), root.join("group/service")]);
+    }
+
+    #[test]
+    fn test_read_head_resolves_loose_and_packed_refs() {
+        let dir = TempDir::new().unwrap();
+        init_repo(dir.path(), "main", "abc123");
+        assert_eq!(
+            read_head(dir.path()).as_deref(),
+            Some("refs/heads/main@abc123")
+        );
+
+        std::fs::write(dir.path().join(".git/HEAD"), "ref: refs/heads/dev\n").unwrap();
+        std::fs::write(
+            dir.path().join(".git/packed-refs"),
+            "# pack-refs with: peeled\nfff000 refs/heads/dev\n",
+        )
+        .unwrap();
+        let head = read_head(dir.path()).unwrap();
+        assert_eq!(head, "refs/heads/dev@fff000");
+        assert_eq!(head_branch(&head).as_deref(), Some("dev"));
+
+        std::fs::write(dir.path().join(".git/HEAD"), "fff000\n").unwrap();
+        let head = read_head(dir.path()).unwrap();
+        assert_eq!(head_branch(&head), None);
+    }
+
+    #[test]
+    fn test_fingerprint_tracks_head_and_manifests() {
+        let dir = TempDir::new().unwrap();
+        init_repo(dir.path(), "main", "a1");
+        let manifests = manifest_files(&node_sources());
+        assert_eq!(
+            manifests.iter().map(String::as_str).collect::<Vec<_>>(),
+            vec![".nvmrc", "package.json"]
+        );
+
+        let before = repo_fingerprint(dir.path(), &manifests);
+        assert!(before.manifest_mtimes.is_empty());
+
+        std::fs::write(dir.path().join(".nvmrc"), "20\n").unwrap();
+        let with_manifest = repo_fingerprint(dir.path(), &manifests);
+        assert_ne!(before, with_manifest);
+        assert!(with_manifest.manifest_mtimes.contains_key(".nvmrc"));
+
+        std::fs::write(dir.path().join(".git/refs/heads/main"), "b2\n").unwrap();
+        assert_ne!(with_manifest, repo_fingerprint(dir.path(), &manifests));
+    }
+
+    #[tokio::test]
+    async fn test_scan_is_incremental() {
+        let dir = TempDir::new().unwrap();
+        let app = dir.path().join("app");
+        let api = dir.path().join("api");
+        init_repo(&app, "main", "a1");
+        init_repo(&api, "main", "b2");
+        std::fs::write(app.join(".nvmrc"), "20.11.0\n").unwrap();
+
+        let sources = node_sources();
+        let mut workspace = Workspace::new("ws", vec![dir.path().display().to_string()]);
+        let (repos, result) = scan_workspace(&workspace, &sources, false).await.unwrap();
+        assert_eq!(result.repos_found, 2);
+        assert_eq!(result.rescanned, 2);
+        let app_repo = repos.iter().find(|r| r.name == "app").unwrap();
+        assert_eq!(app_repo.pins[0].version, "20.11.0");
+        assert_eq!(app_repo.branch.as_deref(), Some("main"));
+        workspace.repos = repos;
+
+        std::fs::write(api.join(".git/refs/heads/main"), "b3\n").unwrap();
+        let (repos, result) = scan_workspace(&workspace, &sources, false).await.unwrap();
+        assert_eq!(result.rescanned, 1);
+        assert_eq!(result.unchanged, 1);
+        workspace.repos = repos;
+
+        let (_, result) = scan_workspace(&workspace, &sources, true).await.unwrap();
+        assert_eq!(result.rescanned, 2);
+
+        workspace.exclude_globs = vec!["api".to_string()];
+        let (repos, result) = scan_workspace(&workspace, &sources, false).await.unwrap();
+        assert_eq!(repos.len(), 1);
+        assert_eq!(result.removed, 1);
+        assert_eq!(result.unchanged, 1);
+    }
+
+    #[test]
+    fn test_plan_deduplicates_and_folds_partial_pins() {
+        let repos = vec![
+            repo("/a", vec![pin("node", "20.11.0"), pin("python", "3.12")]),
+            repo("/b", vec![pin("node", "v20.11.0")]),
+            repo("/c", vec![pin("node", "20"), pin("go", "1.22.0")]),
+        ];
+        let installed = HashMap::from([
+            ("python".to_string(), vec!["3.12.4".to_string()]),
+            ("go".to_string(), vec!["1.21.0".to_string()]),
+        ]);
+
+        assert!(missing_pins(&repos[0], &installed)
+            .iter()
+            .all(|p| p.env_type == "node"));
+
+        let plan = plan_missing_toolchains(&repos, &installed);
+        assert_eq!(
+            plan,
+            vec![
+                WorkspaceToolchain {
+                    env_type: "go".into(),
+                    version: "1.22.0".into(),
+                    repos: vec!["/c".into()],
+                },
+                WorkspaceToolchain {
+                    env_type: "node".into(),
+                    version: "20.11.0".into(),
+                    repos: vec!["/a".into(), "/b".into(), "/c".into()],
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_manager_persists_workspaces() {
+        let dir = TempDir::new().unwrap();
+        let mut manager = WorkspaceManager::new(dir.path());
+        let created = manager
+            .create(
+                Workspace::new("  Work  ", vec!["/src".into(), " ".into()])
+                    .with_exclude_globs(vec!["archive/**".into()]),
+            )
+            .unwrap();
+        assert_eq!(created.name, "Work");
+        assert_eq!(created.root_paths, vec!["/src".to_string()]);
+        assert!(manager
+            .create(Workspace::new("Work", vec!["/other".into()]))
+            .is_err());
+        assert!(manager.create(Workspace::new("Empty", vec![])).is_err());
+
+        manager
+            .set_repos(&created.id, vec![repo("/src/app", vec![pin("node", "20")])])
+            .unwrap();
+        manager.save().await.unwrap();
+
+        let mut reloaded = WorkspaceManager::new(dir.path());
+        reloaded.load().await.unwrap();
+        let workspace = reloaded.get(&created.id).unwrap();
+        assert_eq!(workspace.exclude_globs, vec!["archive/**".to_string()]);
+        assert_eq!(workspace.repos.len(), 1);
+        assert!(workspace.last_scanned_at.is_some());
+
+        reloaded.delete(&created.id).unwrap();
+        assert!(reloaded.delete(&created.id).is_err());
+    }
+}
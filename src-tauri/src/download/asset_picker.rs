@@ -2,18 +2,23 @@
 //!
 //! This module provides intelligent asset matching based on platform, architecture,
 //! and libc type. It uses regex patterns with word boundaries to avoid false matches.
+//! User-defined rules from settings take precedence over these heuristics.
 
-use crate::platform::env::{Architecture, Platform};
+use crate::config::settings::AssetMatchRule;
+use crate::platform::env::{current_arch, current_platform, detect_libc, Architecture, Platform};
 
 /// Libc type for Linux systems (re-exported from platform::env when available)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum LibcType {
     Glibc,
     Musl,
     Unknown,
 }
 use once_cell::sync::Lazy;
-use regex::Regex;
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+use std::sync::{Arc, RwLock};
 
 /// Pre-compiled regex patterns for OS detection
 static OS_LINUX: Lazy<Regex> =
@@ -41,17 +46,168 @@ pub enum LibcType {
 static EXCLUDE_PATTERN: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"(?i)\.(sha256|sha512|sha1|md5|sig|asc|gpg|minisig|sbom)$").unwrap());
 
+/// Score given to an asset matched by the first user rule; later rules score
+/// slightly lower so rule order decides between several matches
+const RULE_BASE_SCORE: i32 = 10_000;
+
+/// User rules loaded from settings, shared by every picker
+static USER_RULES: Lazy<RwLock<Arc<AssetRules>>> =
+    Lazy::new(|| RwLock::new(Arc::new(AssetRules::default())));
+
 /// Trait for asset-like types
 pub trait AssetLike {
     fn name(&self) -> &str;
 }
 
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    platform: Option<Platform>,
+    arch: Option<Architecture>,
+    pattern: String,
+    regex: Regex,
+}
+
+/// Compiled user-defined matching rules and exclusions
+#[derive(Debug, Clone, Default)]
+pub struct AssetRules {
+    rules: Vec<CompiledRule>,
+    exclusions: Vec<(String, Regex)>,
+}
+
+impl AssetRules {
+    /// Compile rules from settings. Invalid patterns are skipped with a
+    /// warning; settings validate them on write, so this only happens for
+    /// hand-edited config files.
+    pub fn compile(rules: &[AssetMatchRule], exclusions: &[String]) -> Self {
+        let build = |pattern: &str| match RegexBuilder::new(pattern).case_insensitive(true).build()
+        {
+            Ok(regex) => Some(regex),
+            Err(e) => {
+                log::warn!("Ignoring invalid asset pattern '{}': {}", pattern, e);
+                None
+            }
+        };
+
+        Self {
+            rules: rules
+                .iter()
+                .filter_map(|rule| {
+                    Some(CompiledRule {
+                        platform: rule.platform,
+                        arch: rule.arch,
+                        pattern: rule.pattern.clone(),
+                        regex: build(&rule.pattern)?,
+                    })
+                })
+                .collect(),
+            exclusions: exclusions
+                .iter()
+                .filter_map(|pattern| Some((pattern.clone(), build(pattern)?)))
+                .collect(),
+        }
+    }
+
+    /// Number of match rules plus exclusions
+    pub fn len(&self) -> usize {
+        self.rules.len() + self.exclusions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty() && self.exclusions.is_empty()
+    }
+
+    fn excluded_by(&self, name: &str) -> Option<&str> {
+        self.exclusions
+            .iter()
+            .find(|(_, regex)| regex.is_match(name))
+            .map(|(pattern, _)| pattern.as_str())
+    }
+
+    /// First rule for this target that matches `name`, with its index
+    fn matching_rule(
+        &self,
+        name: &str,
+        platform: Platform,
+        arch: Architecture,
+    ) -> Option<(usize, &CompiledRule)> {
+        self.rules.iter().enumerate().find(|(_, rule)| {
+            rule.platform.map_or(true, |p| p == platform)
+                && rule.arch.map_or(true, |a| a == arch)
+                && rule.regex.is_match(name)
+        })
+    }
+}
+
+/// Replace the user rules applied by `AssetPicker::for_target`
+pub fn set_user_asset_rules(rules: AssetRules) {
+    if let Ok(mut guard) = USER_RULES.write() {
+        *guard = Arc::new(rules);
+    }
+}
+
+/// Currently configured user rules
+pub fn user_asset_rules() -> Arc<AssetRules> {
+    USER_RULES
+        .read()
+        .map(|guard| guard.clone())
+        .unwrap_or_default()
+}
+
 /// Smart asset picker that selects the best matching asset
 #[derive(Debug, Clone)]
 pub struct AssetPicker {
     platform: Platform,
     arch: Architecture,
     libc: Option<LibcType>,
+    rules: Arc<AssetRules>,
+}
+
+/// Why an asset received its score (or was rejected)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AssetVerdict {
+    /// Matched the user rule at `index`
+    Rule {
+        index: usize,
+        pattern: String,
+    },
+    /// Scored by the built-in platform/arch/libc/format heuristics
+    Heuristic,
+    /// macOS x64 build accepted on Apple Silicon (Rosetta 2)
+    RosettaFallback,
+    /// Checksum, signature or SBOM file
+    ExcludedBuiltin,
+    /// Matched a user exclusion pattern
+    ExcludedByUser {
+        pattern: String,
+    },
+    PlatformMismatch {
+        detected: Platform,
+    },
+    ArchMismatch {
+        detected: Architecture,
+    },
+}
+
+/// Per-asset outcome of `AssetPicker::explain`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetExplanation {
+    pub name: String,
+    /// `None` when the asset can never be picked
+    pub score: Option<i32>,
+    pub verdict: AssetVerdict,
+    pub detected_platform: Option<Platform>,
+    pub detected_arch: Option<Architecture>,
+    pub selected: bool,
+}
+
+struct Evaluation {
+    score: Option<i32>,
+    verdict: AssetVerdict,
+    detected_platform: Option<Platform>,
+    detected_arch: Option<Architecture>,
+    is_fallback: bool,
 }
 
 /// Result of asset matching with score and metadata
@@ -71,7 +227,28 @@ pub fn new(platform: Platform, arch: Architecture) -> Self {
             platform,
             arch,
             libc: None,
+            rules: Arc::default(),
+        }
+    }
+
+    /// Picker for a target with the host libc (on Linux) and the user rules
+    /// from settings
+    pub fn for_target(platform: Platform, arch: Architecture) -> Self {
+        let mut picker = Self::new(platform, arch).with_rules(user_asset_rules());
+        if platform == Platform::Linux {
+            let libc_type = match detect_libc().as_str() {
+                "musl" => LibcType::Musl,
+                "glibc" => LibcType::Glibc,
+                _ => LibcType::Unknown,
+            };
+            picker = picker.with_libc(libc_type);
         }
+        picker
+    }
+
+    /// Picker for the running system
+    pub fn for_host() -> Self {
+        Self::for_target(current_platform(), current_arch())
     }
 
     /// Set the libc type for Linux asset matching
@@ -80,6 +257,28 @@ pub fn with_libc(mut self, libc: LibcType) -> Self {
         self
     }
 
+    /// Apply user-defined rules and exclusions
+    pub fn with_rules(mut self, rules: Arc<AssetRules>) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    pub fn platform(&self) -> Platform {
+        self.platform
+    }
+
+    pub fn arch(&self) -> Architecture {
+        self.arch
+    }
+
+    pub fn libc(&self) -> Option<LibcType> {
+        self.libc
+    }
+
+    pub fn rules(&self) -> &AssetRules {
+        &self.rules
+    }
+
     /// Pick the best matching asset from a list
     pub fn pick_best<'a, T: AssetLike>(&self, assets: &'a [T]) -> Option<&'a T> {
         self.pick_best_with_score(assets).map(|m| m.asset)
@@ -90,49 +289,137 @@ pub fn pick_best_with_score<'a, T: AssetLike>(
         &self,
         assets: &'a [T],
     ) -> Option<AssetMatch<'a, T>> {
-        let mut matches: Vec<AssetMatch<'a, T>> = assets
-            .iter()
-            .filter(|a| !self.is_excluded(a.name()))
-            .filter_map(|a| self.score_asset(a))
-            .collect();
-
-        matches.sort_by_key(|m| -m.score);
-        matches.into_iter().next()
+        self.get_all_matches(assets).into_iter().next()
     }
 
     /// Get all matching assets sorted by score
     pub fn get_all_matches<'a, T: AssetLike>(&self, assets: &'a [T]) -> Vec<AssetMatch<'a, T>> {
-        let mut matches: Vec<AssetMatch<'a, T>> = assets
-            .iter()
-            .filter(|a| !self.is_excluded(a.name()))
-            .filter_map(|a| self.score_asset(a))
-            .collect();
+        let mut matches: Vec<AssetMatch<'a, T>> =
+            assets.iter().filter_map(|a| self.score_asset(a)).collect();
 
         matches.sort_by_key(|m| -m.score);
         matches
     }
 
+    /// Score every asset and report which rule or heuristic decided it, in
+    /// the original order. The asset `pick_best` would return is `selected`.
+    pub fn explain<T: AssetLike>(&self, assets: &[T]) -> Vec<AssetExplanation> {
+        let mut explanations: Vec<AssetExplanation> = assets
+            .iter()
+            .map(|asset| {
+                let eval = self.evaluate(asset.name());
+                AssetExplanation {
+                    name: asset.name().to_string(),
+                    score: eval.score,
+                    verdict: eval.verdict,
+                    detected_platform: eval.detected_platform,
+                    detected_arch: eval.detected_arch,
+                    selected: false,
+                }
+            })
+            .collect();
+
+        // Same tie-break as pick_best: the earliest asset with the top score
+        let mut best: Option<(usize, i32)> = None;
+        for (index, explanation) in explanations.iter().enumerate() {
+            if let Some(score) = explanation.score {
+                if best.map_or(true, |(_, top)| score > top) {
+                    best = Some((index, score));
+                }
+            }
+        }
+        if let Some((index, _)) = best {
+            explanations[index].selected = true;
+        }
+        explanations
+    }
+
     /// Check if an asset should be excluded (checksums, signatures, etc.)
     fn is_excluded(&self, name: &str) -> bool {
         EXCLUDE_PATTERN.is_match(name)
     }
 
-    /// Score an asset based on platform, arch, and libc match
+    /// Score an asset based on user rules, then platform, arch, and libc match
     fn score_asset<'a, T: AssetLike>(&self, asset: &'a T) -> Option<AssetMatch<'a, T>> {
-        let name = asset.name();
-        let mut score = 0;
-        let mut is_fallback = false;
+        let eval = self.evaluate(asset.name());
+        Some(AssetMatch {
+            asset,
+            score: eval.score?,
+            detected_platform: eval.detected_platform,
+            detected_arch: eval.detected_arch,
+            is_fallback: eval.is_fallback,
+        })
+    }
 
+    fn evaluate(&self, name: &str) -> Evaluation {
         let detected_platform = self.detect_platform(name);
         let detected_arch = self.detect_arch(name);
+        let rejected = |verdict| Evaluation {
+            score: None,
+            verdict,
+            detected_platform,
+            detected_arch,
+            is_fallback: false,
+        };
+
+        if self.is_excluded(name) {
+            return rejected(AssetVerdict::ExcludedBuiltin);
+        }
+        if let Some(pattern) = self.rules.excluded_by(name) {
+            return rejected(AssetVerdict::ExcludedByUser {
+                pattern: pattern.to_string(),
+            });
+        }
+
+        // User rules take precedence over every heuristic
+        if let Some((index, rule)) = self.rules.matching_rule(name, self.platform, self.arch) {
+            return Evaluation {
+                score: Some(RULE_BASE_SCORE - index as i32 * 10 + self.format_score(name)),
+                verdict: AssetVerdict::Rule {
+                    index,
+                    pattern: rule.pattern.clone(),
+                },
+                detected_platform,
+                detected_arch,
+                is_fallback: false,
+            };
+        }
+
+        match self.heuristic_score(name, detected_platform, detected_arch) {
+            Ok((score, is_fallback)) => Evaluation {
+                score: Some(score),
+                verdict: if is_fallback {
+                    AssetVerdict::RosettaFallback
+                } else {
+                    AssetVerdict::Heuristic
+                },
+                detected_platform,
+                detected_arch,
+                is_fallback,
+            },
+            Err(verdict) => rejected(verdict),
+        }
+    }
+
+    /// Built-in scoring; returns the score and whether it is a Rosetta fallback
+    fn heuristic_score(
+        &self,
+        name: &str,
+        detected_platform: Option<Platform>,
+        detected_arch: Option<Architecture>,
+    ) -> Result<(i32, bool), AssetVerdict> {
+        let mut score = 0;
+        let mut is_fallback = false;
 
         // Platform matching (required)
         if let Some(plat) = detected_platform {
             if plat == self.platform {
                 score += 100;
+            } else if self.is_rosetta_fallback(detected_platform, detected_arch) {
+                // Lower score than native ARM64
+                return Ok((50, true));
             } else {
-                // No match - but check for macOS ARM fallback
-                return self.try_macos_arm_fallback(asset, detected_platform, detected_arch);
+                return Err(AssetVerdict::PlatformMismatch { detected: plat });
             }
         } else {
             // No platform detected - might be a universal binary or poorly named
@@ -153,7 +440,7 @@ fn score_asset<'a, T: AssetLike>(&self, asset: &'a T) -> Option<AssetMatch<'a, T
                 is_fallback = true;
             } else {
                 // Architecture mismatch - not compatible
-                return None;
+                return Err(AssetVerdict::ArchMismatch { detected: arch });
             }
         } else {
             // No architecture detected - might be universal
@@ -168,40 +455,19 @@ fn score_asset<'a, T: AssetLike>(&self, asset: &'a T) -> Option<AssetMatch<'a, T
         // Format preference bonus
         score += self.format_score(name);
 
-        Some(AssetMatch {
-            asset,
-            score,
-            detected_platform,
-            detected_arch,
-            is_fallback,
-        })
+        Ok((score, is_fallback))
     }
 
-    /// Try macOS ARM to x64 fallback
-    fn try_macos_arm_fallback<'a, T: AssetLike>(
+    /// macOS ARM users can run macOS x64 assets via Rosetta 2
+    fn is_rosetta_fallback(
         &self,
-        asset: &'a T,
         detected_platform: Option<Platform>,
         detected_arch: Option<Architecture>,
-    ) -> Option<AssetMatch<'a, T>> {
-        // Only applies to macOS ARM users
-        if self.platform != Platform::MacOS || self.arch != Architecture::Aarch64 {
-            return None;
-        }
-
-        // Check if asset is macOS x64
-        if detected_platform == Some(Platform::MacOS) && detected_arch == Some(Architecture::X86_64)
-        {
-            Some(AssetMatch {
-                asset,
-                score: 50, // Lower score than native ARM64
-                detected_platform,
-                detected_arch,
-                is_fallback: true,
-            })
-        } else {
-            None
-        }
+    ) -> bool {
+        self.platform == Platform::MacOS
+            && self.arch == Architecture::Aarch64
+            && detected_platform == Some(Platform::MacOS)
+            && detected_arch == Some(Architecture::X86_64)
     }
 
     /// Detect platform from asset name
@@ -654,4 +920,130 @@ fn test_detect_arch_standalone() {
         );
         assert_eq!(detect_arch("app.tar.gz"), None);
     }
+
+    fn rule(
+        platform: Option<Platform>,
+        arch: Option<Architecture>,
+        pattern: &str,
+    ) -> AssetMatchRule {
+        AssetMatchRule {
+            platform,
+            arch,
+            pattern: pattern.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_user_rule_overrides_heuristics() {
+        let assets = make_assets(&[
+            "tool_v1.2.3_linux_amd64.zip",
+            "tool_v1.2.3_lin64_static.tgz",
+            "tool_v1.2.3_win64.zip",
+        ]);
+        let rules = AssetRules::compile(
+            &[rule(
+                Some(Platform::Linux),
+                Some(Architecture::X86_64),
+                r"_lin64_static\.tgz$",
+            )],
+            &[],
+        );
+        let picker =
+            AssetPicker::new(Platform::Linux, Architecture::X86_64).with_rules(Arc::new(rules));
+
+        let best = picker.pick_best_with_score(&assets).unwrap();
+        assert_eq!(best.asset.name, "tool_v1.2.3_lin64_static.tgz");
+        assert!(best.score >= RULE_BASE_SCORE);
+    }
+
+    #[test]
+    fn test_user_rule_scoped_to_target() {
+        let assets = make_assets(&["tool_lin64.tgz", "tool-windows-x86_64.zip"]);
+        let rules = AssetRules::compile(&[rule(Some(Platform::Linux), None, "lin64")], &[]);
+        let picker =
+            AssetPicker::new(Platform::Windows, Architecture::X86_64).with_rules(Arc::new(rules));
+
+        assert_eq!(
+            picker.pick_best(&assets).unwrap().name,
+            "tool-windows-x86_64.zip"
+        );
+    }
+
+    #[test]
+    fn test_earlier_rule_wins() {
+        let assets = make_assets(&["tool-full.zip", "tool-lite.tar.gz"]);
+        let rules =
+            AssetRules::compile(&[rule(None, None, "-full"), rule(None, None, "-lite")], &[]);
+        let picker =
+            AssetPicker::new(Platform::Linux, Architecture::X86_64).with_rules(Arc::new(rules));
+
+        assert_eq!(picker.pick_best(&assets).unwrap().name, "tool-full.zip");
+    }
+
+    #[test]
+    fn test_user_exclusions_and_invalid_patterns() {
+        let assets = make_assets(&["app-linux-x86_64-debug.tar.gz", "app-linux-x86_64.tar.gz"]);
+        let rules = AssetRules::compile(&[rule(None, None, "(broken")], &["-debug".to_string()]);
+        assert_eq!(rules.len(), 1);
+
+        let picker =
+            AssetPicker::new(Platform::Linux, Architecture::X86_64).with_rules(Arc::new(rules));
+        let matches = picker.get_all_matches(&assets);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].asset.name, "app-linux-x86_64.tar.gz");
+    }
+
+    #[test]
+    fn test_explain_reports_every_asset() {
+        let assets = make_assets(&[
+            "app-linux-x86_64-debug.tar.gz",
+            "app-linux-x86_64.tar.gz",
+            "app-linux-aarch64.tar.gz",
+            "app-windows-x86_64.zip",
+            "app.sha256",
+            "app_lin64.tgz",
+        ]);
+        let rules = AssetRules::compile(&[rule(None, None, "lin64")], &["-debug".to_string()]);
+        let picker = AssetPicker::new(Platform::Linux, Architecture::X86_64)
+            .with_libc(LibcType::Glibc)
+            .with_rules(Arc::new(rules));
+
+        let explained = picker.explain(&assets);
+        assert_eq!(explained.len(), assets.len());
+        assert_eq!(
+            explained[0].verdict,
+            AssetVerdict::ExcludedByUser {
+                pattern: "-debug".into()
+            }
+        );
+        assert_eq!(explained[1].verdict, AssetVerdict::Heuristic);
+        assert_eq!(
+            explained[2].verdict,
+            AssetVerdict::ArchMismatch {
+                detected: Architecture::Aarch64
+            }
+        );
+        assert_eq!(
+            explained[3].verdict,
+            AssetVerdict::PlatformMismatch {
+                detected: Platform::Windows
+            }
+        );
+        assert_eq!(explained[4].verdict, AssetVerdict::ExcludedBuiltin);
+        assert_eq!(
+            explained[5].verdict,
+            AssetVerdict::Rule {
+                index: 0,
+                pattern: "lin64".into()
+            }
+        );
+
+        let selected: Vec<&str> = explained
+            .iter()
+            .filter(|e| e.selected)
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(selected, vec!["app_lin64.tgz"]);
+        assert_eq!(picker.pick_best(&assets).unwrap().name, "app_lin64.tgz");
+    }
 }
@@ -0,0 +1,384 @@
+//! Checksum auto-discovery for downloads
+//!
+//! Release pages usually publish digests next to the artifacts, either per
+//! asset (`app.tar.gz.sha256`) or as one sums file covering every asset
+//! (`SHA256SUMS`, `checksums.txt`). This module picks those sidecars out of
+//! a release's asset list, fetches them, and extracts the digest for a given
+//! file so the download can be verified on completion.
+
+use super::task::{CHECKSUM_SOURCE_METADATA_KEY, SIGNATURE_URL_METADATA_KEY};
+use crate::platform::network::{HttpClient, RequestOptions};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Sidecars are tiny; anything bigger is not a checksum file
+const MAX_SIDECAR_BYTES: u64 = 1024 * 1024;
+const SIDECAR_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Kind of sibling file that can vouch for an asset, in preference order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SidecarKind {
+    /// Digest of one asset, e.g. `app.tar.gz.sha256`
+    Digest,
+    /// Digests of several assets, e.g. `SHA256SUMS` or `checksums.txt`
+    SumsFile,
+    /// Detached signature; recorded but carries no digest
+    Signature,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sidecar {
+    pub name: String,
+    pub url: String,
+    pub kind: SidecarKind,
+}
+
+/// One line of a checksum file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChecksumEntry {
+    /// Lowercase hex digest
+    pub digest: String,
+    /// File the digest belongs to; `None` for a bare single-hash file
+    pub file_name: Option<String>,
+}
+
+/// What discovery found for one download
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChecksumDiscovery {
+    pub checksum: Option<String>,
+    /// Sidecar the checksum was read from
+    pub source_url: Option<String>,
+    pub signature_url: Option<String>,
+}
+
+impl ChecksumDiscovery {
+    /// Task metadata recording where verification data came from
+    pub fn metadata(&self) -> HashMap<String, String> {
+        let mut metadata = HashMap::new();
+        if let Some(source) = &self.source_url {
+            metadata.insert(CHECKSUM_SOURCE_METADATA_KEY.to_string(), source.clone());
+        }
+        if let Some(signature) = &self.signature_url {
+            metadata.insert(SIGNATURE_URL_METADATA_KEY.to_string(), signature.clone());
+        }
+        metadata
+    }
+}
+
+fn classify_sidecar(asset_name: &str, candidate: &str) -> Option<SidecarKind> {
+    let asset = asset_name.to_ascii_lowercase();
+    let lower = candidate.to_ascii_lowercase();
+    if lower == asset {
+        return None;
+    }
+    if let Some(suffix) = lower
+        .strip_prefix(&asset)
+        .and_then(|rest| rest.strip_prefix('.'))
+    {
+        return match suffix {
+            "sha256" | "sha256sum" | "sha512" | "sha512sum" => Some(SidecarKind::Digest),
+            "sig" => Some(SidecarKind::Signature),
+            _ => None,
+        };
+    }
+    let is_sums_file = matches!(
+        lower.as_str(),
+        "sha256sums" | "sha256sums.txt" | "sha512sums" | "sha512sums.txt" | "checksums.sha256"
+    ) || lower.ends_with("checksums.txt");
+    is_sums_file.then_some(SidecarKind::SumsFile)
+}
+
+/// Sidecars among a release's `(name, url)` assets that can verify
+/// `asset_name`, best candidate first
+pub fn find_sidecars<'a>(
+    asset_name: &str,
+    siblings: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Vec<Sidecar> {
+    let mut sidecars: Vec<Sidecar> = siblings
+        .into_iter()
+        .filter_map(|(name, url)| {
+            classify_sidecar(asset_name, name).map(|kind| Sidecar {
+                name: name.to_string(),
+                url: url.to_string(),
+                kind,
+            })
+        })
+        .collect();
+    sidecars.sort_by_key(|sidecar| sidecar.kind);
+    sidecars
+}
+
+fn is_hex_digest(value: &str) -> bool {
+    matches!(value.len(), 32 | 40 | 64 | 128) && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// `SHA256 (file) = digest`
+fn parse_bsd_line(line: &str) -> Option<ChecksumEntry> {
+    let (head, digest) = line.rsplit_once(" = ")?;
+    let open = head.find(" (")?;
+    let name = head[open + 2..].strip_suffix(')')?;
+    let digest = digest.trim();
+    is_hex_digest(digest).then(|| ChecksumEntry {
+        digest: digest.to_ascii_lowercase(),
+        file_name: Some(name.to_string()),
+    })
+}
+
+/// `digest  file`, `digest *file`, or a bare `digest`
+fn parse_gnu_line(line: &str) -> Option<ChecksumEntry> {
+    // A leading backslash marks an escaped file name
+    let line = line.strip_prefix('\\').unwrap_or(line);
+    let (digest, rest) = match line.split_once(char::is_whitespace) {
+        Some((digest, rest)) => (digest, rest.trim_start()),
+        None => (line, ""),
+    };
+    if !is_hex_digest(digest) {
+        return None;
+    }
+    let name = rest.strip_prefix('*').unwrap_or(rest);
+    Some(ChecksumEntry {
+        digest: digest.to_ascii_lowercase(),
+        file_name: (!name.is_empty()).then(|| name.to_string()),
+    })
+}
+
+/// Every entry of a checksum file in single-hash, BSD or GNU coreutils format
+pub fn parse_entries(content: &str) -> Vec<ChecksumEntry> {
+    content
+        .lines()
+        .map(|line| line.trim_end_matches('\r').trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| parse_bsd_line(line).or_else(|| parse_gnu_line(line)))
+        .collect()
+}
+
+fn base_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+/// Digest for `file_name` in a checksum file. A bare single-hash file
+/// applies to whatever file it accompanies.
+pub fn parse_checksum_file(content: &str, file_name: &str) -> Option<String> {
+    let entries = parse_entries(content);
+    if let Some(entry) = entries
+        .iter()
+        .find(|e| e.file_name.as_deref().map(base_name) == Some(file_name))
+    {
+        return Some(entry.digest.clone());
+    }
+    match entries.as_slice() {
+        [ChecksumEntry {
+            digest,
+            file_name: None,
+        }] => Some(digest.clone()),
+        _ => None,
+    }
+}
+
+async fn fetch_sidecar(url: &str, headers: &HashMap<String, String>) -> Option<String> {
+    let mut options = RequestOptions::new()
+        .with_timeout(SIDECAR_TIMEOUT)
+        .with_retries(0);
+    for (key, value) in headers {
+        options = options.with_header(key.as_str(), value.as_str());
+    }
+    let response = match HttpClient::new().get_with_options(url, Some(options)).await {
+        Ok(response) => response,
+        Err(e) => {
+            log::debug!("Checksum sidecar {} unavailable: {}", url, e);
+            return None;
+        }
+    };
+    if response
+        .content_length()
+        .is_some_and(|len| len > MAX_SIDECAR_BYTES)
+    {
+        return None;
+    }
+    let bytes = response.bytes().await.ok()?;
+    if bytes.len() as u64 > MAX_SIDECAR_BYTES {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Look for a checksum among the sibling assets of a release download
+pub async fn discover_from_release(
+    asset_name: &str,
+    siblings: &[(String, String)],
+    headers: &HashMap<String, String>,
+) -> ChecksumDiscovery {
+    let sidecars = find_sidecars(
+        asset_name,
+        siblings
+            .iter()
+            .map(|(name, url)| (name.as_str(), url.as_str())),
+    );
+    let mut discovery = ChecksumDiscovery {
+        signature_url: sidecars
+            .iter()
+            .find(|s| s.kind == SidecarKind::Signature)
+            .map(|s| s.url.clone()),
+        ..Default::default()
+    };
+
+    for sidecar in sidecars.iter().filter(|s| s.kind != SidecarKind::Signature) {
+        let Some(content) = fetch_sidecar(&sidecar.url, headers).await else {
+            continue;
+        };
+        if let Some(checksum) = parse_checksum_file(&content, asset_name) {
+            log::info!("Found checksum for {} in {}", asset_name, sidecar.name);
+            discovery.checksum = Some(checksum);
+            discovery.source_url = Some(sidecar.url.clone());
+            break;
+        }
+    }
+    discovery
+}
+
+/// Probe the conventional `<url>.sha256` sidecar of a plain download
+pub async fn probe_conventional(
+    url: &str,
+    file_name: &str,
+    headers: &HashMap<String, String>,
+) -> ChecksumDiscovery {
+    // Signed or parameterized URLs have no predictable sidecar
+    if url.contains('?') || !(url.starts_with("http://") || url.starts_with("https://")) {
+        return ChecksumDiscovery::default();
+    }
+    let sidecar_url = format!("{}.sha256", url);
+    let checksum = match fetch_sidecar(&sidecar_url, headers).await {
+        Some(content) => parse_checksum_file(&content, file_name),
+        None => None,
+    };
+    ChecksumDiscovery {
+        source_url: checksum.as_ref().map(|_| sidecar_url),
+        checksum,
+        signature_url: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHA_A: &str = "a3f1c2d4e5b6978812345678901234567890abcdefabcdefabcdefabcdefabcd";
+    const SHA_B: &str = "0000000000000000000000000000000000000000000000000000000000000001";
+
+    #[test]
+    fn test_find_sidecars_orders_by_preference() {
+        let siblings = [
+            ("tool-linux-x64.tar.gz", "u0"),
+            ("tool-linux-x64.tar.gz.sig", "u1"),
+            ("checksums.txt", "u2"),
+            ("tool-linux-x64.tar.gz.sha256", "u3"),
+            ("tool-windows-x64.zip.sha256", "u4"),
+            ("README.md", "u5"),
+        ];
+        let sidecars = find_sidecars("tool-linux-x64.tar.gz", siblings);
+        let names: Vec<&str> = sidecars.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "tool-linux-x64.tar.gz.sha256",
+                "checksums.txt",
+                "tool-linux-x64.tar.gz.sig"
+            ]
+        );
+        assert_eq!(sidecars[2].kind, SidecarKind::Signature);
+    }
+
+    #[test]
+    fn test_find_sidecars_recognizes_sums_file_names() {
+        for name in ["SHA256SUMS", "sha256sums.txt", "tool_1.2.0_checksums.txt"] {
+            let sidecars = find_sidecars("tool.zip", [(name, "u")]);
+            assert_eq!(sidecars.len(), 1, "{}", name);
+            assert_eq!(sidecars[0].kind, SidecarKind::SumsFile);
+        }
+    }
+
+    #[test]
+    fn test_parse_single_hash() {
+        let content = format!("{}\r\n", SHA_A.to_uppercase());
+        assert_eq!(
+            parse_checksum_file(&content, "tool.zip"),
+            Some(SHA_A.to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_gnu_format_with_binary_marker_and_crlf() {
+        let content = format!(
+            "{}  tool-linux.tar.gz\r\n{} *tool-windows.zip\r\n",
+            SHA_A, SHA_B
+        );
+        assert_eq!(
+            parse_checksum_file(&content, "tool-linux.tar.gz"),
+            Some(SHA_A.to_string())
+        );
+        assert_eq!(
+            parse_checksum_file(&content, "tool-windows.zip"),
+            Some(SHA_B.to_string())
+        );
+        assert_eq!(parse_checksum_file(&content, "tool-macos.zip"), None);
+    }
+
+    #[test]
+    fn test_parse_bsd_format() {
+        let content = format!(
+            "# generated\nSHA256 (tool.zip) = {}\nSHA256 (other file.zip) = {}\n",
+            SHA_A, SHA_B
+        );
+        assert_eq!(
+            parse_checksum_file(&content, "other file.zip"),
+            Some(SHA_B.to_string())
+        );
+        assert_eq!(
+            parse_checksum_file(&content, "tool.zip"),
+            Some(SHA_A.to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_matches_base_name_of_paths() {
+        let content = format!("{}  ./dist/tool.zip\n", SHA_A);
+        assert_eq!(
+            parse_checksum_file(&content, "tool.zip"),
+            Some(SHA_A.to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_digests() {
+        assert!(parse_entries("not-a-hash  tool.zip\nabc123  tool.zip\n").is_empty());
+        assert_eq!(parse_checksum_file("", "tool.zip"), None);
+    }
+
+    #[test]
+    fn test_sidecar_entry_must_name_the_download() {
+        let content = format!("{}  build/out/tool-nightly.zip\n", SHA_A);
+        assert_eq!(parse_checksum_file(&content, "tool.zip"), None);
+        let content = format!("{}  build/out/tool.zip\n", SHA_A);
+        assert_eq!(
+            parse_checksum_file(&content, "tool.zip"),
+            Some(SHA_A.to_string())
+        );
+    }
+
+    #[test]
+    fn test_discovery_metadata() {
+        let discovery = ChecksumDiscovery {
+            checksum: Some(SHA_A.to_string()),
+            source_url: Some("https://example.com/SHA256SUMS".into()),
+            signature_url: None,
+        };
+        let metadata = discovery.metadata();
+        assert_eq!(
+            metadata
+                .get(CHECKSUM_SOURCE_METADATA_KEY)
+                .map(String::as_str),
+            Some("https://example.com/SHA256SUMS")
+        );
+        assert!(!metadata.contains_key(SIGNATURE_URL_METADATA_KEY));
+    }
+}
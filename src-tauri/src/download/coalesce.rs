@@ -0,0 +1,165 @@
+//! Progress event coalescing
+//!
+//! Producers can report progress far faster than a webview can render it.
+//! [`ProgressCoalescer`] keeps only the latest update per key and releases
+//! the pending updates together at most once per interval.
+
+use std::time::{Duration, Instant};
+
+/// Default minimum gap between progress flushes
+pub const DEFAULT_PROGRESS_INTERVAL_MS: u64 = 100;
+
+/// Latest progress per key, released in batches at most once per interval
+#[derive(Debug)]
+pub struct ProgressCoalescer<P> {
+    interval: Duration,
+    /// Pending updates in the order their keys first reported
+    pending: Vec<(String, P)>,
+    last_flush: Option<Instant>,
+}
+
+impl<P> ProgressCoalescer<P> {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            pending: Vec::new(),
+            last_flush: None,
+        }
+    }
+
+    /// Record `progress` for `key`, replacing any update not yet flushed
+    pub fn push(&mut self, key: &str, progress: P) {
+        match self.pending.iter_mut().find(|(k, _)| k == key) {
+            Some((_, slot)) => *slot = progress,
+            None => self.pending.push((key.to_string(), progress)),
+        }
+    }
+
+    /// The pending updates if the interval has passed since the last flush.
+    /// The first update after a quiet period is released immediately.
+    pub fn poll(&mut self, now: Instant) -> Option<Vec<(String, P)>> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        let due = self
+            .last_flush
+            .map_or(true, |t| now.saturating_duration_since(t) >= self.interval);
+        if !due {
+            return None;
+        }
+        self.last_flush = Some(now);
+        Some(std::mem::take(&mut self.pending))
+    }
+
+    /// Remove and return the pending update of `key`, e.g. so the final value
+    /// goes out before that key's terminal state
+    pub fn take(&mut self, key: &str) -> Option<P> {
+        let index = self.pending.iter().position(|(k, _)| k == key)?;
+        Some(self.pending.remove(index).1)
+    }
+
+    /// All pending updates, regardless of the interval
+    pub fn drain(&mut self) -> Vec<(String, P)> {
+        std::mem::take(&mut self.pending)
+    }
+
+    /// When the pending updates become due, `None` when nothing is pending
+    pub fn deadline(&self) -> Option<Instant> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        Some(
+            self.last_flush
+                .map_or_else(Instant::now, |t| t + self.interval),
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_update_is_released_immediately() {
+        let mut c = ProgressCoalescer::new(Duration::from_millis(100));
+        let now = Instant::now();
+        c.push("a", 1);
+        assert_eq!(c.poll(now), Some(vec![("a".to_string(), 1)]));
+        assert!(c.is_empty());
+    }
+
+    #[test]
+    fn test_updates_within_interval_are_merged() {
+        let mut c = ProgressCoalescer::new(Duration::from_millis(100));
+        let start = Instant::now();
+        c.push("a", 1);
+        c.poll(start);
+
+        c.push("a", 2);
+        c.push("b", 10);
+        c.push("a", 3);
+        assert_eq!(c.poll(start + Duration::from_millis(50)), None);
+        assert_eq!(
+            c.poll(start + Duration::from_millis(100)),
+            Some(vec![("a".to_string(), 3), ("b".to_string(), 10)])
+        );
+    }
+
+    #[test]
+    fn test_take_removes_only_that_key() {
+        let mut c = ProgressCoalescer::new(Duration::from_millis(100));
+        c.push("a", 1);
+        c.push("b", 2);
+        assert_eq!(c.take("a"), Some(1));
+        assert_eq!(c.take("a"), None);
+        assert_eq!(c.drain(), vec![("b".to_string(), 2)]);
+    }
+
+    #[test]
+    fn test_deadline_follows_last_flush() {
+        let interval = Duration::from_millis(100);
+        let mut c = ProgressCoalescer::new(interval);
+        let start = Instant::now();
+        assert_eq!(c.deadline(), None);
+        c.push("a", 1);
+        c.poll(start);
+        assert_eq!(c.deadline(), None);
+        c.push("a", 2);
+        assert_eq!(c.deadline(), Some(start + interval));
+    }
+
+    #[test]
+    fn test_high_frequency_producer_is_bounded_by_interval() {
+        let interval = Duration::from_millis(100);
+        let mut c = ProgressCoalescer::new(interval);
+        let start = Instant::now();
+        let mut flushes = 0;
+        let mut last_seen = 0;
+
+        // Three tasks reporting every 10µs for one simulated second
+        for tick in 0..100_000u64 {
+            let now = start + Duration::from_micros(tick * 10);
+            for task in ["a", "b", "c"] {
+                c.push(task, tick);
+            }
+            if let Some(batch) = c.poll(now) {
+                flushes += 1;
+                assert_eq!(batch.len(), 3);
+                last_seen = batch[0].1;
+            }
+        }
+        let rest = c.drain();
+        if !rest.is_empty() {
+            flushes += 1;
+            last_seen = rest[0].1;
+        }
+
+        // One leading flush, one per elapsed interval, one final drain
+        assert!(flushes <= 12, "{} flushes", flushes);
+        assert_eq!(last_seen, 99_999);
+    }
+}
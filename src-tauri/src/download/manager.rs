@@ -1,10 +1,16 @@
 //! Download manager - the main coordinator for all download operations
 
-use super::persistence::QueuePersistence;
+use super::coalesce::{ProgressCoalescer, DEFAULT_PROGRESS_INTERVAL_MS};
+use super::persistence::{QueuePersistence, QueueRestoreSummary};
 use super::queue::{DownloadQueue, QueueStats};
-use super::state::DownloadError;
-use super::task::{DownloadConfig, DownloadProgress, DownloadTask, SpeedTracker};
+use super::stall::{
+    StallDetector, StallSignal, DEFAULT_STALL_RETRY_SECS, DEFAULT_STALL_TIMEOUT_SECS,
+};
+use super::state::{DownloadError, DownloadState};
+use super::task::{DownloadConfig, DownloadJournal, DownloadProgress, DownloadTask, SpeedTracker};
 use super::throttle::SpeedLimiter;
+use crate::cache::{PartialDownload, PartialStatus, PartialValidation};
+use crate::core::archive::{extract_archive_with_progress, throttle_progress, ExtractProgress};
 use crate::platform::fs;
 use futures::StreamExt;
 use reqwest::{Client, StatusCode};
@@ -31,6 +37,9 @@ pub enum DownloadEvent {
         task_id: String,
         progress: DownloadProgress,
     },
+    /// Latest progress of every task that reported since the previous batch;
+    /// see [`coalesce_progress_events`]
+    ProgressBatch { updates: Vec<TaskProgressUpdate> },
     /// Task completed successfully
     TaskCompleted { task_id: String },
     /// Task failed
@@ -46,12 +55,124 @@ pub enum DownloadEvent {
     TaskResumed { task_id: String },
     /// Task was cancelled
     TaskCancelled { task_id: String },
+    /// No bytes arrived for the stall window. `retrying` is set when the
+    /// connection is being re-established.
+    TaskStalled {
+        task_id: String,
+        idle_secs: u64,
+        retrying: bool,
+    },
     /// Task is being extracted
     TaskExtracting { task_id: String },
+    /// Extraction progress, throttled
+    TaskExtractProgress {
+        task_id: String,
+        progress: ExtractProgress,
+    },
     /// Task archive was extracted
     TaskExtracted { task_id: String, files: Vec<String> },
     /// Queue stats updated
     QueueUpdated { stats: QueueStats },
+    /// Persisted tasks from the previous session were restored
+    QueueRestored {
+        resumed: usize,
+        failed: usize,
+        pending: usize,
+    },
+}
+
+/// One task's entry in [`DownloadEvent::ProgressBatch`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskProgressUpdate {
+    pub task_id: String,
+    pub progress: DownloadProgress,
+}
+
+impl DownloadEvent {
+    /// The task this event stops, after which its progress is final
+    fn stopped_task_id(&self) -> Option<&str> {
+        match self {
+            Self::TaskCompleted { task_id }
+            | Self::TaskFailed { task_id, .. }
+            | Self::TaskPaused { task_id }
+            | Self::TaskCancelled { task_id } => Some(task_id),
+            _ => None,
+        }
+    }
+}
+
+fn send_progress_batch(
+    tx: &mpsc::UnboundedSender<DownloadEvent>,
+    batch: Vec<(String, DownloadProgress)>,
+) -> bool {
+    if batch.is_empty() {
+        return true;
+    }
+    let updates = batch
+        .into_iter()
+        .map(|(task_id, progress)| TaskProgressUpdate { task_id, progress })
+        .collect();
+    tx.send(DownloadEvent::ProgressBatch { updates }).is_ok()
+}
+
+async fn run_progress_coalescer(
+    mut rx: mpsc::UnboundedReceiver<DownloadEvent>,
+    tx: mpsc::UnboundedSender<DownloadEvent>,
+    interval: Duration,
+) {
+    let mut coalescer = ProgressCoalescer::new(interval);
+    loop {
+        let event = match coalescer.deadline() {
+            Some(deadline) => {
+                let deadline = tokio::time::Instant::from_std(deadline);
+                match tokio::time::timeout_at(deadline, rx.recv()).await {
+                    Ok(event) => event,
+                    Err(_) => {
+                        let batch = coalescer.poll(Instant::now()).unwrap_or_default();
+                        if !send_progress_batch(&tx, batch) {
+                            return;
+                        }
+                        continue;
+                    }
+                }
+            }
+            None => rx.recv().await,
+        };
+        let Some(event) = event else {
+            send_progress_batch(&tx, coalescer.drain());
+            return;
+        };
+
+        let sent = match event {
+            DownloadEvent::TaskProgress { task_id, progress } => {
+                coalescer.push(&task_id, progress);
+                let batch = coalescer.poll(Instant::now()).unwrap_or_default();
+                send_progress_batch(&tx, batch)
+            }
+            other => {
+                let final_progress = other
+                    .stopped_task_id()
+                    .and_then(|id| Some((id.to_string(), coalescer.take(id)?)));
+                send_progress_batch(&tx, final_progress.into_iter().collect())
+                    && tx.send(other).is_ok()
+            }
+        };
+        if !sent {
+            return;
+        }
+    }
+}
+
+/// Forward `rx` to a new channel, merging `TaskProgress` events into one
+/// `ProgressBatch` at most every `interval`. Every other event passes through
+/// immediately; one that stops a task is preceded by its pending progress.
+pub fn coalesce_progress_events(
+    rx: mpsc::UnboundedReceiver<DownloadEvent>,
+    interval: Duration,
+) -> mpsc::UnboundedReceiver<DownloadEvent> {
+    let (tx, coalesced) = mpsc::unbounded_channel();
+    tokio::spawn(run_progress_coalescer(rx, tx, interval));
+    coalesced
 }
 
 /// Summary returned by graceful shutdown.
@@ -120,6 +241,20 @@ pub struct DownloadManagerConfig {
     pub auto_start: bool,
     /// Progress event throttle interval in milliseconds
     pub progress_interval_ms: u64,
+    /// Seconds without data before a download is reported as stalled
+    #[serde(default = "default_stall_timeout_secs")]
+    pub stall_timeout_secs: u64,
+    /// Seconds without data before a stalled connection is re-established
+    #[serde(default = "default_stall_retry_secs")]
+    pub stall_retry_secs: u64,
+}
+
+fn default_stall_timeout_secs() -> u64 {
+    DEFAULT_STALL_TIMEOUT_SECS
+}
+
+fn default_stall_retry_secs() -> u64 {
+    DEFAULT_STALL_RETRY_SECS
 }
 
 impl Default for DownloadManagerConfig {
@@ -130,11 +265,40 @@ fn default() -> Self {
             default_task_config: DownloadConfig::default(),
             partials_dir: PathBuf::from(".downloads"),
             auto_start: true,
-            progress_interval_ms: 100,
+            progress_interval_ms: DEFAULT_PROGRESS_INTERVAL_MS,
+            stall_timeout_secs: DEFAULT_STALL_TIMEOUT_SECS,
+            stall_retry_secs: DEFAULT_STALL_RETRY_SECS,
         }
     }
 }
 
+/// How often an idle connection is checked for a stall
+const STALL_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Minimum gap between extraction progress events of one task
+const EXTRACT_PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Store a progress update on the task and forward it to listeners
+async fn publish_progress(
+    queue: &Arc<RwLock<DownloadQueue>>,
+    event_tx: &Option<mpsc::UnboundedSender<DownloadEvent>>,
+    task_id: &str,
+    progress: DownloadProgress,
+) {
+    {
+        let mut q = queue.write().await;
+        if let Some(t) = q.get_mut(task_id) {
+            t.progress = progress.clone();
+        }
+    }
+    if let Some(ref tx) = event_tx {
+        let _ = tx.send(DownloadEvent::TaskProgress {
+            task_id: task_id.to_string(),
+            progress,
+        });
+    }
+}
+
 /// Parse a Content-Disposition header value to extract the filename.
 ///
 /// Supports `filename*=UTF-8''encoded` (RFC 5987), `filename="quoted"`, and
@@ -299,6 +463,8 @@ pub struct DownloadManager {
     wake: Arc<Notify>,
     /// Queue persistence for crash recovery
     persistence: Option<Arc<QueuePersistence>>,
+    /// Resolve each task's client from `platform::proxy` instead of `client`
+    shared_clients: bool,
 }
 
 impl Default for DownloadManager {
@@ -329,6 +495,7 @@ pub fn new(config: DownloadManagerConfig, client: Client) -> Self {
             running: Arc::new(AtomicBool::new(false)),
             wake: Arc::new(Notify::new()),
             persistence: None,
+            shared_clients: false,
         }
     }
 
@@ -351,17 +518,57 @@ pub fn enable_persistence(&mut self, dir: &std::path::Path) {
         self.persistence = Some(Arc::new(QueuePersistence::new(dir)));
     }
 
+    /// Pick each task's HTTP client from the shared proxy-aware clients, so
+    /// per-provider proxy overrides and settings changes apply to new downloads.
+    pub fn enable_shared_clients(&mut self) {
+        self.shared_clients = true;
+    }
+
+    /// Throttle through the process-wide limiter, so environment installs that
+    /// download in-process share the configured bandwidth with the queue.
+    pub fn enable_shared_speed_limit(&mut self) {
+        let limit = self
+            .config
+            .try_read()
+            .map(|config| config.speed_limit)
+            .unwrap_or_default();
+        self.speed_limiter = SpeedLimiter::shared();
+        self.speed_limiter.set_limit(limit);
+    }
+
+    fn client_for_task(fallback: &Client, shared_clients: bool, task: &DownloadTask) -> Client {
+        if !shared_clients {
+            return fallback.clone();
+        }
+        // Task providers may carry a source suffix, e.g. `github:owner/repo`
+        match task
+            .provider
+            .as_deref()
+            .and_then(|provider| provider.split(':').next())
+        {
+            Some(provider) => crate::platform::proxy::get_client_for_provider(provider),
+            None => crate::platform::proxy::get_shared_client(),
+        }
+    }
+
     /// Load persisted tasks from previous session and add them to the queue.
-    pub async fn load_persisted_tasks(&self) -> usize {
+    ///
+    /// Tasks are reconciled against their partial files first; resumable
+    /// ones come back paused unless `auto_resume` queues them again. A single
+    /// `QueueRestored` event summarizes the outcome.
+    pub async fn load_persisted_tasks(&self, auto_resume: bool) -> QueueRestoreSummary {
         let persistence = match &self.persistence {
             Some(p) => p.clone(),
-            None => return 0,
+            None => return QueueRestoreSummary::default(),
         };
+        let partials_dir = self.config.read().await.partials_dir.clone();
 
-        match persistence.load().await {
-            Ok(tasks) => {
-                let count = tasks.len();
-                for task in tasks {
+        match persistence.restore(&partials_dir).await {
+            Ok((tasks, summary)) => {
+                for mut task in tasks {
+                    if auto_resume && task.state == DownloadState::Paused {
+                        task.state = DownloadState::Queued;
+                    }
                     let task_id = task.id.clone();
                     let speed_limit = task.config.speed_limit;
                     {
@@ -373,11 +580,18 @@ pub async fn load_persisted_tasks(&self) -> usize {
                         controls.insert(task_id, TaskControl::new(speed_limit));
                     }
                 }
-                count
+                if summary.total() > 0 {
+                    self.emit(DownloadEvent::QueueRestored {
+                        resumed: summary.resumed,
+                        failed: summary.failed,
+                        pending: summary.pending,
+                    });
+                }
+                summary
             }
             Err(e) => {
                 log::warn!("Failed to load persisted download queue: {}", e);
-                0
+                QueueRestoreSummary::default()
             }
         }
     }
@@ -565,6 +779,15 @@ pub async fn get_task(&self, task_id: &str) -> Option<DownloadTask> {
         queue.get(task_id).cloned()
     }
 
+    /// Take the network bytes a task received since this was last called
+    pub async fn take_received_bytes(&self, task_id: &str) -> u64 {
+        let mut queue = self.queue.write().await;
+        queue
+            .get_mut(task_id)
+            .map(|t| std::mem::take(&mut t.received_bytes))
+            .unwrap_or(0)
+    }
+
     /// Get all tasks
     pub async fn list_tasks(&self) -> Vec<DownloadTask> {
         let queue = self.queue.read().await;
@@ -794,6 +1017,56 @@ pub async fn cleanup_stale_partials(&self, max_age: Duration) -> usize {
         cleaned
     }
 
+    /// Check every resume journal in the partials directory against its file.
+    /// With `repair`, inconsistent files are truncated to their last verified
+    /// checkpoint. Journals of actively downloading tasks are skipped, and
+    /// journals whose file is gone are removed when repairing.
+    pub async fn validate_partials(&self, repair: bool) -> Vec<PartialValidation> {
+        let partials_dir = self.config.read().await.partials_dir.clone();
+        let active: Vec<PathBuf> = {
+            let queue = self.queue.read().await;
+            queue
+                .list_by_state(&crate::download::DownloadState::Downloading)
+                .iter()
+                .map(|task| task.journal_path(&partials_dir))
+                .collect()
+        };
+
+        let mut reports = Vec::new();
+        let Ok(mut entries) = tokio::fs::read_dir(&partials_dir).await else {
+            return reports;
+        };
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            let is_journal = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.ends_with(DownloadJournal::FILE_SUFFIX));
+            if !is_journal || active.contains(&path) {
+                continue;
+            }
+            let Some(mut record) = PartialDownload::load_from(&path).await else {
+                continue;
+            };
+            match record.verify(repair).await {
+                Ok(report) => {
+                    if repair {
+                        if report.status == PartialStatus::Missing {
+                            DownloadJournal::discard(&path).await;
+                        } else if report.repaired {
+                            if let Err(e) = record.save_to(&path).await {
+                                log::warn!("Failed to update download journal {:?}: {}", path, e);
+                            }
+                        }
+                    }
+                    reports.push(report);
+                }
+                Err(e) => log::warn!("Failed to validate partial download {:?}: {}", path, e),
+            }
+        }
+        reports
+    }
+
     /// Graceful shutdown: stop the processor loop and preserve recoverable queue state.
     pub async fn shutdown(&self) -> ShutdownOutcome {
         self.running.store(false, Ordering::SeqCst);
@@ -849,6 +1122,7 @@ pub async fn start(&self) {
 
         let queue = self.queue.clone();
         let client = self.client.clone();
+        let shared_clients = self.shared_clients;
         let speed_limiter = self.speed_limiter.clone();
         let task_controls = self.task_controls.clone();
         let event_tx = self.event_tx.clone();
@@ -887,7 +1161,7 @@ pub async fn start(&self) {
 
                         if let Some(control) = control {
                             let queue = queue.clone();
-                            let client = client.clone();
+                            let client = Self::client_for_task(&client, shared_clients, &task);
                             let speed_limiter = speed_limiter.clone();
                             let event_tx = event_tx.clone();
                             let cfg = config.clone();
@@ -953,6 +1227,11 @@ async fn download_worker(
     ) {
         let task_id = task.id.clone();
 
+        // Progress restored from an earlier run was accounted by that run
+        if let Some(t) = queue.write().await.get_mut(&task_id) {
+            t.begin_attempt(t.progress.downloaded_bytes);
+        }
+
         // Emit started event
         if let Some(ref tx) = event_tx {
             let _ = tx.send(DownloadEvent::TaskStarted {
@@ -996,6 +1275,9 @@ async fn download_worker(
                     seconds: task.config.timeout_secs,
                 }),
             };
+            if let Some(t) = queue.write().await.get_mut(&task_id) {
+                t.settle_attempt();
+            }
 
             if result.is_ok() {
                 if url_idx > 0 {
@@ -1004,6 +1286,12 @@ async fn download_worker(
                         task_id,
                         url_idx
                     );
+                    if let Some(t) = queue.write().await.get_mut(&task_id) {
+                        t.metadata.insert(
+                            crate::download::task::EFFECTIVE_URL_METADATA_KEY.to_string(),
+                            try_url.clone(),
+                        );
+                    }
                 }
                 break;
             }
@@ -1038,9 +1326,20 @@ async fn download_worker(
                             .to_path_buf()
                     });
 
-                    match crate::core::installer::extract_archive(
+                    let on_progress = event_tx.clone().map(|tx| {
+                        let task_id = task_id.clone();
+                        throttle_progress(EXTRACT_PROGRESS_INTERVAL, move |progress| {
+                            let _ = tx.send(DownloadEvent::TaskExtractProgress {
+                                task_id: task_id.clone(),
+                                progress,
+                            });
+                        })
+                    });
+
+                    match extract_archive_with_progress(
                         &effective_destination,
                         &extract_dest,
+                        on_progress,
                     )
                     .await
                     {
@@ -1180,11 +1479,20 @@ async fn do_download(
                 })?;
         }
 
+        let journal_path = if task.config.allow_resume {
+            Some(task.journal_path(&config.read().await.partials_dir))
+        } else {
+            None
+        };
+
         let mut resume_from = if task.config.allow_resume && requested_destination.exists() {
-            let size = tokio::fs::metadata(&requested_destination)
-                .await
-                .map(|m| m.len())
-                .unwrap_or(0);
+            let size = match journal_path.as_deref() {
+                Some(path) => DownloadJournal::resumable_size(path, &requested_destination).await,
+                None => tokio::fs::metadata(&requested_destination)
+                    .await
+                    .map(|m| m.len())
+                    .unwrap_or(0),
+            };
             if size > 0 {
                 Some(size)
             } else {
@@ -1381,10 +1689,14 @@ async fn do_download(
             }
         }
 
-        // Read configurable progress interval
-        let progress_interval = {
+        // Read configurable progress interval and stall thresholds
+        let (progress_interval, stall_timeout, stall_retry) = {
             let cfg = config.read().await;
-            Duration::from_millis(cfg.progress_interval_ms)
+            (
+                Duration::from_millis(cfg.progress_interval_ms),
+                Duration::from_secs(cfg.stall_timeout_secs),
+                Duration::from_secs(cfg.stall_retry_secs),
+            )
         };
 
         let baseline_progress = DownloadProgress::new(resume_baseline, total_size, 0.0);
@@ -1392,6 +1704,7 @@ async fn do_download(
             let mut q = queue.write().await;
             if let Some(t) = q.get_mut(task_id) {
                 t.progress = baseline_progress.clone();
+                t.begin_attempt(resume_baseline);
             }
         }
         if let Some(ref tx) = event_tx {
@@ -1431,6 +1744,12 @@ async fn do_download(
                     .map_err(|e| DownloadError::FileSystem {
                         message: e.to_string(),
                     })?;
+                // Segments fill the file out of order, so nothing is resumable:
+                // journal offset 0 makes a crash restart instead of appending.
+                if let Some(path) = journal_path.clone() {
+                    DownloadJournal::begin(path, url, &effective_destination, &file, 0, total_size)
+                        .await;
+                }
             }
 
             let mut handles = Vec::new();
@@ -1549,8 +1868,13 @@ async fn do_download(
             let progress_last = last_progress.clone();
             let progress_control_cancelled = control.cancelled.clone();
 
+            let progress_control_paused = control.paused.clone();
+
+            // Segments are not reconnected individually; a stall is only
+            // reported and the task timeout still applies.
             let progress_handle = tokio::spawn(async move {
                 let mut speed_tracker = SpeedTracker::new();
+                let mut stall = StallDetector::new(stall_timeout, stall_retry, 0, Instant::now());
                 loop {
                     tokio::time::sleep(Duration::from_millis(200)).await;
                     if progress_control_cancelled.load(Ordering::SeqCst) {
@@ -1565,21 +1889,34 @@ async fn do_download(
                     drop(last);
 
                     let dl = progress_downloaded.load(Ordering::Relaxed);
-                    let speed = speed_tracker.update(dl);
-
-                    let progress = DownloadProgress::new(dl, Some(progress_total), speed);
-                    {
-                        let mut q = progress_queue.write().await;
-                        if let Some(t) = q.get_mut(&progress_task_id) {
-                            t.progress = progress.clone();
+                    let now = Instant::now();
+                    if progress_control_paused.load(Ordering::SeqCst) {
+                        stall.reset(dl, now);
+                    } else if stall.observe(dl, now) == Some(StallSignal::Stalled) {
+                        log::warn!(
+                            "Download {} stalled: no data for {}s",
+                            progress_task_id,
+                            stall.idle(now).as_secs()
+                        );
+                        if let Some(ref tx) = progress_event_tx {
+                            let _ = tx.send(DownloadEvent::TaskStalled {
+                                task_id: progress_task_id.clone(),
+                                idle_secs: stall.idle(now).as_secs(),
+                                retrying: false,
+                            });
                         }
                     }
-                    if let Some(ref tx) = progress_event_tx {
-                        let _ = tx.send(DownloadEvent::TaskProgress {
-                            task_id: progress_task_id.clone(),
-                            progress,
-                        });
-                    }
+                    let speed = speed_tracker.update_at(dl, now);
+
+                    let progress = DownloadProgress::new(dl, Some(progress_total), speed)
+                        .with_stalled(stall.is_stalled());
+                    publish_progress(
+                        &progress_queue,
+                        &progress_event_tx,
+                        &progress_task_id,
+                        progress,
+                    )
+                    .await;
 
                     if dl >= progress_total {
                         break;
@@ -1611,6 +1948,9 @@ async fn do_download(
 
             if let Some(err) = first_error {
                 let _ = tokio::fs::remove_file(&effective_destination).await;
+                if let Some(path) = journal_path.as_deref() {
+                    DownloadJournal::discard(path).await;
+                }
                 return Err(err);
             }
         } else {
@@ -1632,26 +1972,158 @@ async fn do_download(
                 })?
             };
 
+            let mut journal = match journal_path.clone() {
+                Some(path) if supports_resume => Some(
+                    DownloadJournal::begin(
+                        path,
+                        url,
+                        &effective_destination,
+                        &file,
+                        resume_baseline,
+                        total_size,
+                    )
+                    .await,
+                ),
+                Some(path) => {
+                    // Not resumable; drop any journal left by an earlier attempt
+                    DownloadJournal::discard(&path).await;
+                    None
+                }
+                None => None,
+            };
+
             let mut downloaded = resume_baseline;
             let mut last_progress_update = Instant::now();
-            let mut speed_tracker = SpeedTracker::new();
+            let mut speed_tracker = SpeedTracker::new().with_baseline(resume_baseline);
+            let mut stall =
+                StallDetector::new(stall_timeout, stall_retry, downloaded, Instant::now());
+            let mut stall_retries: u32 = 0;
+            let mut stall_ticker = tokio::time::interval(STALL_CHECK_INTERVAL);
+            stall_ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
             let mut stream = response.bytes_stream();
 
-            while let Some(chunk_result) = stream.next().await {
+            loop {
+                let chunk_result = tokio::select! {
+                    next = stream.next() => match next {
+                        Some(chunk_result) => chunk_result,
+                        None => break,
+                    },
+                    _ = stall_ticker.tick() => {
+                        let now = Instant::now();
+                        if control.is_paused() {
+                            stall.reset(downloaded, now);
+                            continue;
+                        }
+                        match stall.observe(downloaded, now) {
+                            Some(StallSignal::RetryDue) => {
+                                // Keep what we have whatever happens next
+                                if let Some(journal) = journal.as_mut() {
+                                    journal.checkpoint(&mut file, downloaded).await;
+                                }
+                                let idle_secs = stall.idle(now).as_secs();
+                                if !supports_resume || stall_retries >= task.config.max_retries {
+                                    return Err(DownloadError::Network {
+                                        message: format!("Connection stalled for {}s", idle_secs),
+                                    });
+                                }
+                                if let Some(ref tx) = event_tx {
+                                    let _ = tx.send(DownloadEvent::TaskStalled {
+                                        task_id: task_id.clone(),
+                                        idle_secs,
+                                        retrying: true,
+                                    });
+                                }
+
+                                let backoff_secs = 2u64.pow(stall_retries).min(60);
+                                stall_retries += 1;
+                                if let Some(t) = queue.write().await.get_mut(task_id) {
+                                    t.retries += 1;
+                                }
+                                log::info!(
+                                    "Download {} stalled for {}s, reconnecting in {}s (attempt {})",
+                                    task_id,
+                                    idle_secs,
+                                    backoff_secs,
+                                    stall_retries
+                                );
+                                tokio::time::sleep(Duration::from_secs(backoff_secs)).await;
+                                if control.is_cancelled() {
+                                    return Err(DownloadError::Interrupted);
+                                }
+
+                                let resumed = send_request(Some(downloaded)).await?;
+                                if let Some(err) = check_rate_limit(&resumed) {
+                                    return Err(err);
+                                }
+                                if resumed.status() != StatusCode::PARTIAL_CONTENT {
+                                    return Err(DownloadError::Network {
+                                        message: format!(
+                                            "Server did not resume stalled download ({})",
+                                            resumed.status()
+                                        ),
+                                    });
+                                }
+                                stream = resumed.bytes_stream();
+                                stall.rearm(Instant::now());
+                            }
+                            Some(StallSignal::Stalled) => {
+                                log::warn!(
+                                    "Download {} stalled: no data for {}s",
+                                    task_id,
+                                    stall.idle(now).as_secs()
+                                );
+                                if let Some(ref tx) = event_tx {
+                                    let _ = tx.send(DownloadEvent::TaskStalled {
+                                        task_id: task_id.clone(),
+                                        idle_secs: stall.idle(now).as_secs(),
+                                        retrying: false,
+                                    });
+                                }
+                            }
+                            Some(StallSignal::Recovered) | None => {}
+                        }
+                        // Keep the speed decaying while nothing arrives
+                        if stall.is_stalled() {
+                            let speed = speed_tracker.update_at(downloaded, now);
+                            let progress = DownloadProgress::new(downloaded, total_size, speed)
+                                .with_stalled(true);
+                            publish_progress(queue, event_tx, task_id, progress).await;
+                            last_progress_update = now;
+                        }
+                        continue;
+                    }
+                };
+
                 if control.is_cancelled() {
                     return Err(DownloadError::Interrupted);
                 }
 
-                while control.is_paused() {
-                    if control.is_cancelled() {
-                        return Err(DownloadError::Interrupted);
+                if control.is_paused() {
+                    if let Some(journal) = journal.as_mut() {
+                        journal.checkpoint(&mut file, downloaded).await;
                     }
-                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    while control.is_paused() {
+                        if control.is_cancelled() {
+                            return Err(DownloadError::Interrupted);
+                        }
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                    }
+                    // Time spent paused is not a stall
+                    stall.reset(downloaded, Instant::now());
                 }
 
-                let chunk = chunk_result.map_err(|e| DownloadError::Network {
-                    message: e.to_string(),
-                })?;
+                let chunk = match chunk_result {
+                    Ok(chunk) => chunk,
+                    Err(e) => {
+                        // Keep what we have for the retry's resume
+                        if let Some(journal) = journal.as_mut() {
+                            journal.checkpoint(&mut file, downloaded).await;
+                        }
+                        return Err(DownloadError::Network {
+                            message: e.to_string(),
+                        });
+                    }
+                };
 
                 let chunk_len = chunk.len() as u64;
                 // Global speed limit
@@ -1679,25 +2151,16 @@ async fn do_download(
 
                 downloaded += chunk_len;
 
-                if last_progress_update.elapsed() >= progress_interval {
-                    let speed = speed_tracker.update(downloaded);
+                if let Some(journal) = journal.as_mut().filter(|j| j.is_due(downloaded)) {
+                    journal.checkpoint(&mut file, downloaded).await;
+                }
 
+                let recovered =
+                    stall.observe(downloaded, Instant::now()) == Some(StallSignal::Recovered);
+                if recovered || last_progress_update.elapsed() >= progress_interval {
+                    let speed = speed_tracker.update(downloaded);
                     let progress = DownloadProgress::new(downloaded, total_size, speed);
-
-                    {
-                        let mut q = queue.write().await;
-                        if let Some(t) = q.get_mut(task_id) {
-                            t.progress = progress.clone();
-                        }
-                    }
-
-                    if let Some(ref tx) = event_tx {
-                        let _ = tx.send(DownloadEvent::TaskProgress {
-                            task_id: task_id.clone(),
-                            progress,
-                        });
-                    }
-
+                    publish_progress(queue, event_tx, task_id, progress).await;
                     last_progress_update = Instant::now();
                 }
             }
@@ -1721,14 +2184,32 @@ async fn do_download(
                 if &actual != expected {
                     // Remove corrupted file
                     let _ = tokio::fs::remove_file(&effective_destination).await;
-                    return Err(DownloadError::ChecksumMismatch {
-                        expected: expected.clone(),
-                        actual,
+                    if let Some(path) = journal_path.as_deref() {
+                        DownloadJournal::discard(path).await;
+                    }
+                    let source = task
+                        .metadata
+                        .get(crate::download::task::CHECKSUM_SOURCE_METADATA_KEY)
+                        .cloned();
+                    return Err(match source {
+                        Some(source) => DownloadError::VerificationFailed {
+                            expected: expected.clone(),
+                            actual,
+                            source,
+                        },
+                        None => DownloadError::ChecksumMismatch {
+                            expected: expected.clone(),
+                            actual,
+                        },
                     });
                 }
             }
         }
 
+        if let Some(path) = journal_path.as_deref() {
+            DownloadJournal::discard(path).await;
+        }
+
         Ok(effective_destination)
     }
 }
@@ -2080,9 +2561,9 @@ async fn test_download_manager_shutdown_persists_and_restores_recoverable_queue(
         let mut restored_manager =
             DownloadManager::new(DownloadManagerConfig::default(), Client::new());
         restored_manager.enable_persistence(tmp.path());
-        let restored = restored_manager.load_persisted_tasks().await;
+        let restored = restored_manager.load_persisted_tasks(true).await;
 
-        assert_eq!(restored, 1);
+        assert_eq!(restored.total(), 1);
         let restored_tasks = restored_manager.list_tasks().await;
         assert_eq!(restored_tasks.len(), 1);
         assert_eq!(restored_tasks[0].state, DownloadState::Queued);
@@ -2546,6 +3027,8 @@ fn test_download_manager_config_serde_roundtrip() {
             partials_dir: PathBuf::from("/custom/dir"),
             auto_start: false,
             progress_interval_ms: 200,
+            stall_timeout_secs: 45,
+            stall_retry_secs: 90,
             default_task_config: DownloadConfig {
                 max_retries: 5,
                 ..Default::default()
@@ -2559,6 +3042,18 @@ fn test_download_manager_config_serde_roundtrip() {
         assert!(!deserialized.auto_start);
         assert_eq!(deserialized.partials_dir, PathBuf::from("/custom/dir"));
         assert_eq!(deserialized.default_task_config.max_retries, 5);
+        assert_eq!(deserialized.stall_timeout_secs, 45);
+        assert_eq!(deserialized.stall_retry_secs, 90);
+    }
+
+    #[test]
+    fn test_download_manager_config_stall_defaults() {
+        let config: DownloadManagerConfig = serde_json::from_str(
+            r#"{"maxConcurrent":4,"speedLimit":0,"defaultTaskConfig":{},"partialsDir":".downloads","autoStart":true,"progressIntervalMs":100}"#,
+        )
+        .unwrap();
+        assert_eq!(config.stall_timeout_secs, DEFAULT_STALL_TIMEOUT_SECS);
+        assert_eq!(config.stall_retry_secs, DEFAULT_STALL_RETRY_SECS);
     }
 
     #[test]
@@ -2574,6 +3069,12 @@ fn test_download_event_serde_all_variants() {
                 task_id: "t1".into(),
                 progress: DownloadProgress::new(50, Some(100), 10.0),
             },
+            DownloadEvent::ProgressBatch {
+                updates: vec![TaskProgressUpdate {
+                    task_id: "t1".into(),
+                    progress: DownloadProgress::new(60, Some(100), 10.0),
+                }],
+            },
             DownloadEvent::TaskCompleted {
                 task_id: "t1".into(),
             },
@@ -2592,9 +3093,23 @@ fn test_download_event_serde_all_variants() {
             DownloadEvent::TaskCancelled {
                 task_id: "t1".into(),
             },
+            DownloadEvent::TaskStalled {
+                task_id: "t1".into(),
+                idle_secs: 30,
+                retrying: false,
+            },
             DownloadEvent::TaskExtracting {
                 task_id: "t1".into(),
             },
+            DownloadEvent::TaskExtractProgress {
+                task_id: "t1".into(),
+                progress: ExtractProgress {
+                    current_file: "bin/node".into(),
+                    files_done: 3,
+                    total_files: None,
+                    bytes_written: 1024,
+                },
+            },
             DownloadEvent::TaskExtracted {
                 task_id: "t1".into(),
                 files: vec!["a.txt".into()],
@@ -2813,6 +3328,165 @@ async fn test_download_manager_persistence_not_enabled() {
         // persist_queue_force should be a no-op when persistence is None
         manager.persist_queue_force().await;
         // load should return 0 when persistence is None
-        assert_eq!(manager.load_persisted_tasks().await, 0);
+        assert_eq!(manager.load_persisted_tasks(true).await.total(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_download_manager_restore_without_auto_resume_emits_summary() {
+        let tmp = tempfile::tempdir().unwrap();
+        let persistence = QueuePersistence::new(tmp.path());
+
+        let mut in_flight = DownloadTask::new(
+            "https://example.com/partial.zip".to_string(),
+            tmp.path().join("partial.zip"),
+            "Partial".to_string(),
+        );
+        in_flight.mark_started();
+        in_flight.update_progress(100, Some(1000), 0.0);
+        tokio::fs::write(&in_flight.destination, vec![0u8; 100])
+            .await
+            .unwrap();
+        let mut vanished = DownloadTask::new(
+            "https://example.com/vanished.zip".to_string(),
+            tmp.path().join("vanished.zip"),
+            "Vanished".to_string(),
+        );
+        vanished.mark_started();
+        vanished.update_progress(100, Some(1000), 0.0);
+        let pending = DownloadTask::new(
+            "https://example.com/pending.zip".to_string(),
+            tmp.path().join("pending.zip"),
+            "Pending".to_string(),
+        );
+        let in_flight_id = in_flight.id.clone();
+        persistence
+            .save(&[in_flight, vanished, pending], true)
+            .await
+            .unwrap();
+
+        let mut manager = DownloadManager::new(
+            DownloadManagerConfig {
+                partials_dir: tmp.path().join("partials"),
+                ..Default::default()
+            },
+            Client::new(),
+        );
+        let mut rx = manager.create_event_channel();
+        manager.enable_persistence(tmp.path());
+        let summary = manager.load_persisted_tasks(false).await;
+
+        assert_eq!(
+            (summary.resumed, summary.pending, summary.failed),
+            (1, 1, 1)
+        );
+        let task = manager.get_task(&in_flight_id).await.unwrap();
+        assert_eq!(task.state, DownloadState::Paused);
+        assert_eq!(task.progress.downloaded_bytes, 100);
+        match rx.try_recv().unwrap() {
+            DownloadEvent::QueueRestored {
+                resumed,
+                failed,
+                pending,
+            } => assert_eq!((resumed, failed, pending), (1, 1, 1)),
+            other => panic!("unexpected event: {:?}", other),
+        }
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_coalesce_progress_events_bounds_high_frequency_producer() {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let mut out = coalesce_progress_events(rx, Duration::from_secs(1));
+
+        for downloaded in 1..=20_000u64 {
+            for task_id in ["a", "b"] {
+                tx.send(DownloadEvent::TaskProgress {
+                    task_id: task_id.into(),
+                    progress: DownloadProgress::new(downloaded, Some(20_000), 0.0),
+                })
+                .unwrap();
+            }
+        }
+        tx.send(DownloadEvent::TaskCompleted {
+            task_id: "a".into(),
+        })
+        .unwrap();
+        drop(tx);
+
+        let mut events = Vec::new();
+        while let Some(event) = out.recv().await {
+            events.push(event);
+        }
+
+        let batches = events
+            .iter()
+            .filter(|e| matches!(e, DownloadEvent::ProgressBatch { .. }))
+            .count();
+        // Leading flush, the final value before completion, the close flush
+        assert!(batches <= 3, "{} batches for 40000 updates", batches);
+        assert!(!events
+            .iter()
+            .any(|e| matches!(e, DownloadEvent::TaskProgress { .. })));
+
+        // The final value of "a" arrives right before its completion
+        let completed = events
+            .iter()
+            .position(|e| matches!(e, DownloadEvent::TaskCompleted { .. }))
+            .unwrap();
+        match &events[completed - 1] {
+            DownloadEvent::ProgressBatch { updates } => {
+                let a = updates.iter().find(|u| u.task_id == "a").unwrap();
+                assert_eq!(a.progress.downloaded_bytes, 20_000);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+        // "b" is flushed when the producer closes
+        match events.last().unwrap() {
+            DownloadEvent::ProgressBatch { updates } => {
+                assert_eq!(updates.len(), 1);
+                assert_eq!(updates[0].task_id, "b");
+                assert_eq!(updates[0].progress.downloaded_bytes, 20_000);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_coalesce_progress_events_flushes_after_interval() {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let mut out = coalesce_progress_events(rx, Duration::from_millis(50));
+
+        for downloaded in [10, 20, 30] {
+            tx.send(DownloadEvent::TaskProgress {
+                task_id: "t1".into(),
+                progress: DownloadProgress::new(downloaded, Some(100), 0.0),
+            })
+            .unwrap();
+        }
+
+        let mut seen = Vec::new();
+        for _ in 0..2 {
+            let event = tokio::time::timeout(Duration::from_secs(1), out.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            match event {
+                DownloadEvent::ProgressBatch { updates } => {
+                    seen.push(updates[0].progress.downloaded_bytes)
+                }
+                other => panic!("unexpected event: {:?}", other),
+            }
+        }
+        // Leading update at once, the rest merged into the next tick
+        assert_eq!(seen, vec![10, 30]);
+        // Other events are not delayed
+        tx.send(DownloadEvent::TaskStarted {
+            task_id: "t2".into(),
+        })
+        .unwrap();
+        assert!(matches!(
+            tokio::time::timeout(Duration::from_millis(20), out.recv()).await,
+            Ok(Some(DownloadEvent::TaskStarted { .. }))
+        ));
     }
 }
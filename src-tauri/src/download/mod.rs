@@ -3,29 +3,39 @@
 //! This module provides a comprehensive download management system with:
 //! - Download queue management
 //! - Pause/resume support
-//! - Progress tracking
+//! - Progress tracking with stall detection
+//! - Coalesced progress events
 //! - Speed limiting
 //! - Retry mechanisms
 //! - Download history
 //! - Smart asset picking
+//! - Checksum auto-discovery from release sidecars
 
 mod asset_picker;
+pub mod checksum_discovery;
+mod coalesce;
 mod manager;
 mod persistence;
 mod queue;
+mod stall;
 mod state;
 pub(crate) mod task;
 mod throttle;
 
 pub use asset_picker::{
-    detect_arch, detect_platform, AssetLike, AssetMatch, AssetPicker, LibcType,
+    detect_arch, detect_platform, set_user_asset_rules, user_asset_rules, AssetExplanation,
+    AssetLike, AssetMatch, AssetPicker, AssetRules, AssetVerdict, LibcType,
 };
-pub use manager::{DownloadEvent, DownloadManager, DownloadManagerConfig, ShutdownOutcome};
-pub use persistence::QueuePersistence;
+pub use coalesce::{ProgressCoalescer, DEFAULT_PROGRESS_INTERVAL_MS};
+pub use manager::{
+    coalesce_progress_events, DownloadEvent, DownloadManager, DownloadManagerConfig,
+    ShutdownOutcome, TaskProgressUpdate,
+};
+pub use persistence::{QueuePersistence, QueueRestoreSummary};
 pub use queue::DownloadQueue;
 pub use state::{DownloadError, DownloadState};
 pub use task::{
-    ArtifactArch, ArtifactKind, ArtifactPlatform, ArtifactProfile, DownloadConfig,
+    ArtifactArch, ArtifactKind, ArtifactPlatform, ArtifactProfile, DownloadConfig, DownloadJournal,
     DownloadProgress, DownloadTask, FollowUpAction, InstallIntent, PostAction, SourceDescriptor,
     SourceKind,
 };
@@ -1,7 +1,9 @@
 //! Download queue persistence - save/restore incomplete tasks across app restarts
 
-use super::state::DownloadState;
-use super::task::DownloadTask;
+use super::state::{DownloadError, DownloadState};
+use super::task::{DownloadJournal, DownloadProgress, DownloadTask};
+use crate::platform::fs;
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -44,23 +46,9 @@ pub async fn save(&self, tasks: &[DownloadTask], force: bool) -> Result<(), Stri
         let persistable: Vec<&DownloadTask> =
             tasks.iter().filter(|t| !t.state.is_terminal()).collect();
 
-        let json = serde_json::to_string_pretty(&persistable)
-            .map_err(|e| format!("Failed to serialize queue: {}", e))?;
-
-        if let Some(parent) = self.file_path.parent() {
-            tokio::fs::create_dir_all(parent)
-                .await
-                .map_err(|e| format!("Failed to create directory: {}", e))?;
-        }
-
-        // Write atomically: write to temp then rename
-        let tmp_path = self.file_path.with_extension("json.tmp");
-        tokio::fs::write(&tmp_path, json.as_bytes())
+        fs::atomic_write_json(&self.file_path, &persistable, true)
             .await
             .map_err(|e| format!("Failed to write queue file: {}", e))?;
-        tokio::fs::rename(&tmp_path, &self.file_path)
-            .await
-            .map_err(|e| format!("Failed to rename queue file: {}", e))?;
 
         let mut last = self.last_write.lock().await;
         *last = Instant::now();
@@ -68,64 +56,163 @@ pub async fn save(&self, tasks: &[DownloadTask], force: bool) -> Result<(), Stri
         Ok(())
     }
 
-    /// Load persisted tasks from disk.
+    /// Load persisted tasks from disk exactly as they were saved.
     ///
-    /// All loaded tasks are reset to `Queued` state (Downloading tasks were interrupted).
-    /// Returns an empty vec if the file doesn't exist.
+    /// Use [`QueuePersistence::restore`] to bring them back into a consistent
+    /// state. Returns an empty vec if the file doesn't exist.
     pub async fn load(&self) -> Result<Vec<DownloadTask>, String> {
-        if !self.file_path.exists() {
-            return Ok(Vec::new());
-        }
-
-        let data = tokio::fs::read_to_string(&self.file_path)
-            .await
-            .map_err(|e| format!("Failed to read queue file: {}", e))?;
-
-        if data.trim().is_empty() {
-            return Ok(Vec::new());
+        match fs::read_json_with_backup(&self.file_path).await {
+            Ok(loaded) => Ok(loaded.into_value("Download queue").unwrap_or_default()),
+            Err(e) => {
+                // An empty file without a usable backup is an empty queue
+                let empty = tokio::fs::read_to_string(&self.file_path)
+                    .await
+                    .is_ok_and(|data| data.trim().is_empty());
+                if empty {
+                    Ok(Vec::new())
+                } else {
+                    Err(format!("Failed to deserialize queue: {}", e))
+                }
+            }
         }
+    }
 
-        let mut tasks: Vec<DownloadTask> = serde_json::from_str(&data)
-            .map_err(|e| format!("Failed to deserialize queue: {}", e))?;
-
-        // Reset all tasks to Queued — they were interrupted
+    /// Load persisted tasks and reconcile each one against disk with
+    /// [`reconcile_task`]. `partials_dir` holds the resume journals.
+    pub async fn restore(
+        &self,
+        partials_dir: &Path,
+    ) -> Result<(Vec<DownloadTask>, QueueRestoreSummary), String> {
+        let mut tasks = self.load().await?;
+        let mut summary = QueueRestoreSummary::default();
         for task in &mut tasks {
-            match &task.state {
-                DownloadState::Downloading | DownloadState::Queued | DownloadState::Paused => {
-                    task.state = DownloadState::Queued;
-                    task.error = None;
-                    task.failure_reason_code = None;
-                }
-                _ => {
-                    // Terminal tasks shouldn't be in the file, but handle gracefully
-                    task.state = DownloadState::Queued;
-                    task.error = None;
-                    task.failure_reason_code = None;
-                    task.retries = 0;
-                }
+            match reconcile_task(task, partials_dir).await {
+                RestoreOutcome::Resumable => summary.resumed += 1,
+                RestoreOutcome::Pending => summary.pending += 1,
+                RestoreOutcome::Failed => summary.failed += 1,
             }
         }
 
-        log::info!(
-            "Restored {} download tasks from {}",
-            tasks.len(),
-            self.file_path.display()
-        );
+        if !tasks.is_empty() {
+            log::info!(
+                "Restored {} download tasks from {} ({} resumable, {} pending, {} failed)",
+                tasks.len(),
+                self.file_path.display(),
+                summary.resumed,
+                summary.pending,
+                summary.failed
+            );
+        }
 
-        Ok(tasks)
+        Ok((tasks, summary))
     }
 
     /// Remove the persistence file (e.g. when all tasks are done).
     pub async fn clear(&self) -> Result<(), String> {
-        if self.file_path.exists() {
-            tokio::fs::remove_file(&self.file_path)
-                .await
-                .map_err(|e| format!("Failed to remove queue file: {}", e))?;
+        for path in [self.file_path.clone(), fs::backup_path(&self.file_path)] {
+            if path.exists() {
+                tokio::fs::remove_file(&path)
+                    .await
+                    .map_err(|e| format!("Failed to remove queue file: {}", e))?;
+            }
         }
         Ok(())
     }
 }
 
+/// Counts of restored tasks by outcome
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueRestoreSummary {
+    /// Tasks with a usable partial file (or that were paused), restored as
+    /// `Paused` at their resumable offset
+    pub resumed: usize,
+    /// Tasks that had not started, restored as `Queued`
+    pub pending: usize,
+    /// Tasks whose partial file vanished, restored as `Failed`
+    pub failed: usize,
+}
+
+impl QueueRestoreSummary {
+    pub fn total(&self) -> usize {
+        self.resumed + self.pending + self.failed
+    }
+}
+
+/// How a persisted task was brought back by [`reconcile_task`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestoreOutcome {
+    Resumable,
+    Pending,
+    Failed,
+}
+
+/// Bring a task loaded from the queue file back into a consistent state.
+///
+/// No worker survives a restart, so in-flight and paused tasks become
+/// `Paused` with progress set to the bytes that can actually be resumed:
+/// the journal-verified size when a journal exists, otherwise the size on
+/// disk. Tasks that had written bytes but whose file is gone fail with
+/// [`DownloadError::ArtifactMissing`]. Tasks that never started stay queued.
+pub async fn reconcile_task(task: &mut DownloadTask, partials_dir: &Path) -> RestoreOutcome {
+    task.error = None;
+    task.failure_reason_code = None;
+
+    let was_paused = match task.state {
+        DownloadState::Downloading => false,
+        DownloadState::Paused => true,
+        DownloadState::Queued if task.progress.downloaded_bytes == 0 => {
+            return RestoreOutcome::Pending;
+        }
+        DownloadState::Queued => false,
+        _ => {
+            // Terminal tasks shouldn't be in the file, but handle gracefully
+            task.state = DownloadState::Queued;
+            task.retries = 0;
+            task.progress = DownloadProgress::new(0, task.progress.total_bytes, 0.0);
+            return RestoreOutcome::Pending;
+        }
+    };
+
+    let journal_path = task.journal_path(partials_dir);
+    let recorded = task.progress.downloaded_bytes;
+    let total = task.progress.total_bytes;
+
+    if tokio::fs::metadata(&task.destination).await.is_err() {
+        DownloadJournal::discard(&journal_path).await;
+        if recorded > 0 {
+            task.progress = DownloadProgress::new(0, total, 0.0);
+            task.mark_failed(DownloadError::ArtifactMissing {
+                path: task.destination.display().to_string(),
+            });
+            return RestoreOutcome::Failed;
+        }
+        if !was_paused {
+            task.state = DownloadState::Queued;
+            return RestoreOutcome::Pending;
+        }
+        task.state = DownloadState::Paused;
+        return RestoreOutcome::Resumable;
+    }
+
+    let offset = if task.config.allow_resume {
+        DownloadJournal::resumable_size(&journal_path, &task.destination).await
+    } else {
+        0
+    };
+    if offset != recorded {
+        log::warn!(
+            "Restored download {:?} recorded {} bytes but {} are resumable",
+            task.destination,
+            recorded,
+            offset
+        );
+    }
+    task.progress = DownloadProgress::new(offset, total, 0.0);
+    task.state = DownloadState::Paused;
+    RestoreOutcome::Resumable
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -185,22 +272,106 @@ async fn test_terminal_tasks_not_saved() {
         let loaded = persistence.load().await.unwrap();
 
         assert_eq!(loaded.len(), 2);
-        assert!(loaded.iter().all(|t| t.state == DownloadState::Queued));
+        assert_eq!(loaded[0].state, DownloadState::Queued);
+        assert_eq!(loaded[1].state, DownloadState::Paused);
+    }
+
+    /// Task for `name` whose destination lives in `dir`, saved mid-download
+    /// after `downloaded` of `total` bytes
+    fn in_flight_task(dir: &Path, name: &str, downloaded: u64, total: u64) -> DownloadTask {
+        let mut task = DownloadTask::new(
+            format!("https://example.com/{}.zip", name),
+            dir.join(format!("{}.zip", name)),
+            name.to_string(),
+        );
+        task.mark_started();
+        task.update_progress(downloaded, Some(total), 1024.0);
+        task
     }
 
     #[tokio::test]
-    async fn test_downloading_tasks_reset_to_queued() {
+    async fn test_restore_never_started_task_stays_pending() {
         let tmp = TempDir::new().unwrap();
         let persistence = QueuePersistence::new(tmp.path());
 
         let mut task = create_test_task("downloading");
-        task.mark_started(); // state = Downloading
+        task.mark_started(); // crashed before writing any bytes
+
+        persistence
+            .save(&[task, create_test_task("queued")], true)
+            .await
+            .unwrap();
+        let (restored, summary) = persistence.restore(tmp.path()).await.unwrap();
+
+        assert_eq!(summary.pending, 2);
+        assert_eq!(summary.total(), 2);
+        assert!(restored.iter().all(|t| t.state == DownloadState::Queued));
+    }
+
+    #[tokio::test]
+    async fn test_restore_crash_mid_download_pauses_at_disk_offset() {
+        let tmp = TempDir::new().unwrap();
+        let persistence = QueuePersistence::new(tmp.path());
+
+        // The queue file claims 4096 bytes, but only 3000 reached the disk
+        let task = in_flight_task(tmp.path(), "partial", 4096, 10_000);
+        tokio::fs::write(&task.destination, vec![0u8; 3000])
+            .await
+            .unwrap();
 
         persistence.save(&[task], true).await.unwrap();
-        let loaded = persistence.load().await.unwrap();
+        let (restored, summary) = persistence.restore(tmp.path()).await.unwrap();
 
-        assert_eq!(loaded.len(), 1);
-        assert_eq!(loaded[0].state, DownloadState::Queued);
+        assert_eq!(summary.resumed, 1);
+        assert_eq!(restored[0].state, DownloadState::Paused);
+        assert_eq!(restored[0].progress.downloaded_bytes, 3000);
+        assert_eq!(restored[0].progress.total_bytes, Some(10_000));
+        assert!(restored[0].state.can_resume());
+    }
+
+    #[tokio::test]
+    async fn test_restore_missing_artifact_fails_task() {
+        let tmp = TempDir::new().unwrap();
+        let persistence = QueuePersistence::new(tmp.path());
+
+        // Partial file deleted while the app was closed
+        let task = in_flight_task(tmp.path(), "vanished", 2048, 10_000);
+        persistence.save(&[task], true).await.unwrap();
+        let (restored, summary) = persistence.restore(tmp.path()).await.unwrap();
+
+        assert_eq!(summary.failed, 1);
+        assert!(matches!(
+            restored[0].state,
+            DownloadState::Failed {
+                recoverable: true,
+                ..
+            }
+        ));
+        assert_eq!(
+            restored[0].failure_reason_code.as_deref(),
+            Some("artifact_missing")
+        );
+        assert_eq!(restored[0].progress.downloaded_bytes, 0);
+    }
+
+    #[tokio::test]
+    async fn test_restore_paused_task_without_resume_support_restarts() {
+        let tmp = TempDir::new().unwrap();
+        let persistence = QueuePersistence::new(tmp.path());
+
+        let mut task = in_flight_task(tmp.path(), "no-resume", 512, 1024);
+        task.config.allow_resume = false;
+        task.mark_paused();
+        tokio::fs::write(&task.destination, vec![0u8; 512])
+            .await
+            .unwrap();
+
+        persistence.save(&[task], true).await.unwrap();
+        let (restored, summary) = persistence.restore(tmp.path()).await.unwrap();
+
+        assert_eq!(summary.resumed, 1);
+        assert_eq!(restored[0].state, DownloadState::Paused);
+        assert_eq!(restored[0].progress.downloaded_bytes, 0);
     }
 
     #[tokio::test]
@@ -249,4 +420,33 @@ async fn test_empty_file() {
         let loaded = persistence.load().await.unwrap();
         assert!(loaded.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_torn_queue_file_recovers_from_backup() {
+        let tmp = TempDir::new().unwrap();
+        let persistence = QueuePersistence::new(tmp.path());
+
+        persistence
+            .save(&[create_test_task("file1")], true)
+            .await
+            .unwrap();
+        persistence
+            .save(
+                &[create_test_task("file1"), create_test_task("file2")],
+                true,
+            )
+            .await
+            .unwrap();
+        // Simulate a crash halfway through the last write
+        tokio::fs::write(tmp.path().join("download_queue.json"), "[{\"id\":")
+            .await
+            .unwrap();
+
+        let loaded = persistence.load().await.unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].name, "file1");
+
+        persistence.clear().await.unwrap();
+        assert!(!tmp.path().join("download_queue.json.bak").exists());
+    }
 }
@@ -0,0 +1,202 @@
+//! Stall detection for in-flight downloads
+//!
+//! A download is stalled once no bytes have arrived for `stall_after`. If it
+//! stays idle until `retry_after`, the worker is told to reconnect. Time is
+//! passed in explicitly so the state machine can be driven by tests.
+
+use std::time::{Duration, Instant};
+
+/// Default idle window before a download is reported as stalled
+pub const DEFAULT_STALL_TIMEOUT_SECS: u64 = 30;
+/// Default idle window before a stalled connection is re-established
+pub const DEFAULT_STALL_RETRY_SECS: u64 = 60;
+
+/// Transition reported by [`StallDetector::observe`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StallSignal {
+    /// No bytes for the stall window
+    Stalled,
+    /// Bytes are flowing again after a stall
+    Recovered,
+    /// Still idle at the retry threshold; reconnect
+    RetryDue,
+}
+
+#[derive(Debug)]
+pub(crate) struct StallDetector {
+    stall_after: Duration,
+    retry_after: Duration,
+    last_bytes: u64,
+    last_activity: Instant,
+    stalled: bool,
+    retry_signalled: bool,
+}
+
+impl StallDetector {
+    /// `retry_after` is clamped so it never fires before `stall_after`
+    pub fn new(stall_after: Duration, retry_after: Duration, bytes: u64, now: Instant) -> Self {
+        Self {
+            stall_after,
+            retry_after: retry_after.max(stall_after),
+            last_bytes: bytes,
+            last_activity: now,
+            stalled: false,
+            retry_signalled: false,
+        }
+    }
+
+    pub fn is_stalled(&self) -> bool {
+        self.stalled
+    }
+
+    /// How long no bytes have arrived
+    pub fn idle(&self, now: Instant) -> Duration {
+        now.saturating_duration_since(self.last_activity)
+    }
+
+    /// Feed the cumulative byte count, returning a transition if one happened
+    pub fn observe(&mut self, bytes: u64, now: Instant) -> Option<StallSignal> {
+        if bytes != self.last_bytes {
+            self.last_bytes = bytes;
+            self.last_activity = now;
+            self.retry_signalled = false;
+            if self.stalled {
+                self.stalled = false;
+                return Some(StallSignal::Recovered);
+            }
+            return None;
+        }
+
+        let idle = self.idle(now);
+        if !self.stalled {
+            if idle >= self.stall_after {
+                self.stalled = true;
+                return Some(StallSignal::Stalled);
+            }
+            return None;
+        }
+        if !self.retry_signalled && idle >= self.retry_after {
+            self.retry_signalled = true;
+            return Some(StallSignal::RetryDue);
+        }
+        None
+    }
+
+    /// Restart the retry window after reconnecting. The download stays
+    /// stalled until bytes arrive.
+    pub fn rearm(&mut self, now: Instant) {
+        self.last_activity = now;
+        self.retry_signalled = false;
+    }
+
+    /// Forget any idle time, e.g. after the task was paused
+    pub fn reset(&mut self, bytes: u64, now: Instant) {
+        self.last_bytes = bytes;
+        self.last_activity = now;
+        self.stalled = false;
+        self.retry_signalled = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detector(start: Instant) -> StallDetector {
+        StallDetector::new(Duration::from_secs(30), Duration::from_secs(60), 0, start)
+    }
+
+    #[test]
+    fn test_no_signal_while_bytes_flow() {
+        let start = Instant::now();
+        let mut d = detector(start);
+        for i in 1..=10u64 {
+            assert_eq!(
+                d.observe(i * 100, start + Duration::from_secs(i * 10)),
+                None
+            );
+        }
+        assert!(!d.is_stalled());
+    }
+
+    #[test]
+    fn test_stalled_after_window_then_retry_due() {
+        let start = Instant::now();
+        let mut d = detector(start);
+
+        assert_eq!(d.observe(0, start + Duration::from_secs(29)), None);
+        assert_eq!(
+            d.observe(0, start + Duration::from_secs(30)),
+            Some(StallSignal::Stalled)
+        );
+        assert!(d.is_stalled());
+        // Reported once
+        assert_eq!(d.observe(0, start + Duration::from_secs(45)), None);
+        assert_eq!(
+            d.observe(0, start + Duration::from_secs(60)),
+            Some(StallSignal::RetryDue)
+        );
+        assert_eq!(d.observe(0, start + Duration::from_secs(90)), None);
+    }
+
+    #[test]
+    fn test_recovered_when_bytes_resume() {
+        let start = Instant::now();
+        let mut d = detector(start);
+        d.observe(0, start + Duration::from_secs(31));
+
+        assert_eq!(
+            d.observe(10, start + Duration::from_secs(32)),
+            Some(StallSignal::Recovered)
+        );
+        assert!(!d.is_stalled());
+        assert_eq!(
+            d.idle(start + Duration::from_secs(33)),
+            Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    fn test_rearm_waits_a_full_retry_window() {
+        let start = Instant::now();
+        let mut d = detector(start);
+        d.observe(0, start + Duration::from_secs(30));
+        d.observe(0, start + Duration::from_secs(60));
+
+        let reconnected = start + Duration::from_secs(65);
+        d.rearm(reconnected);
+        assert!(d.is_stalled());
+        assert_eq!(d.observe(0, reconnected + Duration::from_secs(59)), None);
+        assert_eq!(
+            d.observe(0, reconnected + Duration::from_secs(60)),
+            Some(StallSignal::RetryDue)
+        );
+    }
+
+    #[test]
+    fn test_reset_clears_stall() {
+        let start = Instant::now();
+        let mut d = detector(start);
+        d.observe(0, start + Duration::from_secs(40));
+
+        let resumed = start + Duration::from_secs(300);
+        d.reset(0, resumed);
+        assert!(!d.is_stalled());
+        assert_eq!(d.observe(0, resumed + Duration::from_secs(10)), None);
+    }
+
+    #[test]
+    fn test_retry_never_precedes_stall() {
+        let start = Instant::now();
+        let mut d = StallDetector::new(Duration::from_secs(30), Duration::from_secs(5), 0, start);
+        assert_eq!(d.observe(0, start + Duration::from_secs(5)), None);
+        assert_eq!(
+            d.observe(0, start + Duration::from_secs(30)),
+            Some(StallSignal::Stalled)
+        );
+        assert_eq!(
+            d.observe(0, start + Duration::from_secs(31)),
+            Some(StallSignal::RetryDue)
+        );
+    }
+}
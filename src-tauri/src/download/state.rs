@@ -81,10 +81,18 @@ pub enum DownloadError {
     FileSystem { message: String },
     /// Checksum verification failed
     ChecksumMismatch { expected: String, actual: String },
+    /// File did not match a checksum auto-discovered from `source`
+    VerificationFailed {
+        expected: String,
+        actual: String,
+        source: String,
+    },
     /// Not enough disk space
     InsufficientSpace { required: u64, available: u64 },
     /// Download was interrupted
     Interrupted,
+    /// Partial file of a restored download is gone
+    ArtifactMissing { path: String },
     /// URL is invalid or inaccessible
     InvalidUrl { url: String },
     /// Server returned an error status
@@ -111,6 +119,17 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                     expected, actual
                 )
             }
+            Self::VerificationFailed {
+                expected,
+                actual,
+                source,
+            } => {
+                write!(
+                    f,
+                    "Verification against {} failed: expected {}, got {}",
+                    source, expected, actual
+                )
+            }
             Self::InsufficientSpace {
                 required,
                 available,
@@ -123,6 +142,9 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 )
             }
             Self::Interrupted => write!(f, "Download interrupted"),
+            Self::ArtifactMissing { path } => {
+                write!(f, "Partial download artifact missing: {}", path)
+            }
             Self::InvalidUrl { url } => write!(f, "Invalid URL: {}", url),
             Self::HttpError { status, message } => {
                 write!(f, "HTTP error {}: {}", status, message)
@@ -148,8 +170,10 @@ pub fn reason_code(&self) -> &'static str {
             Self::Network { .. } => "network_error",
             Self::FileSystem { .. } => "filesystem_error",
             Self::ChecksumMismatch { .. } => "checksum_mismatch",
+            Self::VerificationFailed { .. } => "verification_failed",
             Self::InsufficientSpace { .. } => "insufficient_space",
             Self::Interrupted => "interrupted",
+            Self::ArtifactMissing { .. } => "artifact_missing",
             Self::InvalidUrl { .. } => "invalid_url",
             Self::HttpError { status, .. } => match *status {
                 401 => "unauthorized",
@@ -173,7 +197,8 @@ pub fn is_recoverable(&self) -> bool {
             Self::Network { .. }
             | Self::Timeout { .. }
             | Self::RateLimited { .. }
-            | Self::Interrupted => true,
+            | Self::Interrupted
+            | Self::ArtifactMissing { .. } => true,
             Self::HttpError { status, .. } if *status >= 500 => true,
             _ => false,
         }
@@ -254,6 +279,12 @@ fn test_download_error_is_recoverable() {
             actual: "def".into()
         }
         .is_recoverable());
+        assert!(!DownloadError::VerificationFailed {
+            expected: "abc".into(),
+            actual: "def".into(),
+            source: "https://example.com/SHA256SUMS".into()
+        }
+        .is_recoverable());
         assert!(!DownloadError::InvalidUrl { url: "bad".into() }.is_recoverable());
         assert!(!DownloadError::HttpError {
             status: 404,
@@ -288,6 +319,15 @@ fn test_download_error_reason_code_mapping() {
             .reason_code(),
             "checksum_mismatch"
         );
+        assert_eq!(
+            DownloadError::VerificationFailed {
+                expected: "a".into(),
+                actual: "b".into(),
+                source: "https://example.com/a.sha256".into()
+            }
+            .reason_code(),
+            "verification_failed"
+        );
         assert_eq!(
             DownloadError::InsufficientSpace {
                 required: 100,
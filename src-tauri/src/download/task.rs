@@ -1,39 +1,54 @@
 //! Download task definition
 
 use super::state::{DownloadError, DownloadState};
+use crate::cache::{PartialDownload, PartialStatus};
 use crate::platform::disk::{format_duration, format_size};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 /// Exponential Weighted Moving Average speed tracker.
 ///
 /// Produces a smoothed download speed that responds quickly to changes
-/// while filtering out per-chunk jitter.  Not serialized — used only
+/// while filtering out per-chunk jitter.  The weight of each sample depends
+/// on the time it covers, so the smoothing window stays the same whether
+/// updates arrive every 100 ms or every second.  Not serialized — used only
 /// inside download workers.
 pub(crate) struct SpeedTracker {
     prev_speed: f64,
     prev_bytes: u64,
     prev_time: Instant,
-    alpha: f64,
+    /// Seconds after which an old sample has ~37% weight left
+    time_constant: f64,
 }
 
 impl SpeedTracker {
-    /// Create a new tracker.  `alpha` controls responsiveness (0.3 = moderate).
+    /// Create a new tracker with a ~3 second smoothing window.
     pub fn new() -> Self {
         Self {
             prev_speed: 0.0,
             prev_bytes: 0,
             prev_time: Instant::now(),
-            alpha: 0.3,
+            time_constant: 3.0,
         }
     }
 
+    /// Start counting from `bytes` already on disk, so a resumed download
+    /// does not report the existing data as one huge burst.
+    pub fn with_baseline(mut self, bytes: u64) -> Self {
+        self.prev_bytes = bytes;
+        self
+    }
+
     /// Feed the current cumulative byte count and get the smoothed speed (bytes/s).
     pub fn update(&mut self, current_bytes: u64) -> f64 {
-        let now = Instant::now();
-        let dt = now.duration_since(self.prev_time).as_secs_f64();
+        self.update_at(current_bytes, Instant::now())
+    }
+
+    /// [`SpeedTracker::update`] with an explicit clock reading.
+    pub fn update_at(&mut self, current_bytes: u64, now: Instant) -> f64 {
+        let dt = now.saturating_duration_since(self.prev_time).as_secs_f64();
         if dt < 0.05 {
             return self.prev_speed;
         }
@@ -44,7 +59,8 @@ pub fn update(&mut self, current_bytes: u64) -> f64 {
         self.prev_speed = if self.prev_speed == 0.0 {
             instant_speed
         } else {
-            self.alpha * instant_speed + (1.0 - self.alpha) * self.prev_speed
+            let alpha = 1.0 - (-dt / self.time_constant).exp();
+            alpha * instant_speed + (1.0 - alpha) * self.prev_speed
         };
 
         self.prev_bytes = current_bytes;
@@ -264,6 +280,9 @@ pub struct DownloadProgress {
     pub eta_secs: Option<u64>,
     /// Progress percentage (0-100)
     pub percent: f32,
+    /// No bytes have arrived for the stall window
+    #[serde(default)]
+    pub stalled: bool,
 }
 
 impl DownloadProgress {
@@ -291,9 +310,19 @@ pub fn new(downloaded: u64, total: Option<u64>, speed: f64) -> Self {
             speed,
             eta_secs,
             percent,
+            stalled: false,
         }
     }
 
+    /// Mark the progress as stalled; a stalled download has no ETA.
+    pub fn with_stalled(mut self, stalled: bool) -> Self {
+        self.stalled = stalled;
+        if stalled {
+            self.eta_secs = None;
+        }
+        self
+    }
+
     /// Format downloaded size as human-readable string
     pub fn downloaded_human(&self) -> String {
         format_size(self.downloaded_bytes)
@@ -377,8 +406,26 @@ pub struct DownloadTask {
     /// Resolved artifact profile used by UI follow-up actions
     #[serde(default)]
     pub artifact_profile: Option<ArtifactProfile>,
+    /// Bytes received over the network and not yet recorded as bandwidth usage
+    #[serde(default)]
+    pub received_bytes: u64,
+    /// Progress offset the running attempt has been accounted up to
+    #[serde(default)]
+    pub attempt_offset: u64,
 }
 
+/// Task metadata key holding the URL (primary or mirror) a download was served from
+pub const EFFECTIVE_URL_METADATA_KEY: &str = "effective_url";
+
+/// Task metadata keys naming the environment version a download is installed for
+pub const ENV_TYPE_METADATA_KEY: &str = "env_type";
+pub const ENV_VERSION_METADATA_KEY: &str = "env_version";
+
+/// Task metadata key holding the sidecar URL an auto-discovered checksum was read from
+pub const CHECKSUM_SOURCE_METADATA_KEY: &str = "checksum_source";
+/// Task metadata key holding the URL of a detached signature published with the asset
+pub const SIGNATURE_URL_METADATA_KEY: &str = "signature_url";
+
 impl DownloadTask {
     /// Create a new download task
     pub fn new(url: String, destination: PathBuf, name: String) -> Self {
@@ -408,6 +455,8 @@ pub fn new(url: String, destination: PathBuf, name: String) -> Self {
             install_intent: None,
             source_descriptor: None,
             artifact_profile: None,
+            received_bytes: 0,
+            attempt_offset: 0,
         }
     }
 
@@ -456,6 +505,19 @@ pub fn mark_cancelled(&mut self) {
         self.failure_reason_code = None;
     }
 
+    /// Start accounting an attempt whose progress begins at `offset`, e.g. a
+    /// resumed partial file that was already counted by an earlier attempt
+    pub fn begin_attempt(&mut self, offset: u64) {
+        self.attempt_offset = offset;
+    }
+
+    /// Add the bytes received since the attempt began to `received_bytes`
+    pub fn settle_attempt(&mut self) {
+        let downloaded = self.progress.downloaded_bytes;
+        self.received_bytes += downloaded.saturating_sub(self.attempt_offset);
+        self.attempt_offset = downloaded;
+    }
+
     /// Increment retry count
     pub fn increment_retry(&mut self) {
         self.retries += 1;
@@ -466,6 +528,27 @@ pub fn can_retry(&self) -> bool {
         self.retries < self.config.max_retries
     }
 
+    /// URL the bytes were actually fetched from; differs from `url` when a mirror was used
+    pub fn effective_url(&self) -> &str {
+        self.metadata
+            .get(EFFECTIVE_URL_METADATA_KEY)
+            .map(String::as_str)
+            .unwrap_or(&self.url)
+    }
+
+    /// Provider label used for bandwidth accounting
+    pub fn usage_provider(&self) -> String {
+        self.provider
+            .clone()
+            .or_else(|| {
+                self.source_descriptor
+                    .as_ref()
+                    .and_then(|source| source.provider.clone())
+            })
+            .filter(|provider| !provider.trim().is_empty())
+            .unwrap_or_else(|| "direct".to_string())
+    }
+
     /// Get the filename from destination
     pub fn filename(&self) -> String {
         self.destination
@@ -473,6 +556,141 @@ pub fn filename(&self) -> String {
             .map(|s| s.to_string_lossy().to_string())
             .unwrap_or_else(|| "unknown".to_string())
     }
+
+    /// Location of this task's resume journal inside `partials_dir`
+    pub fn journal_path(&self, partials_dir: &Path) -> PathBuf {
+        partials_dir.join(format!("{}{}", self.id, DownloadJournal::FILE_SUFFIX))
+    }
+}
+
+/// Crash-safe resume journal for a download.
+///
+/// Progress is written only after the bytes it covers were fsynced, so the
+/// journal can lag behind the file but never run ahead of it. Journaling is
+/// best-effort: failures are logged and the download carries on.
+pub struct DownloadJournal {
+    path: PathBuf,
+    record: PartialDownload,
+    last_checkpoint: u64,
+}
+
+impl DownloadJournal {
+    pub const FILE_SUFFIX: &'static str = ".journal.json";
+    /// Bytes written between fsynced checkpoints
+    pub const CHECKPOINT_BYTES: u64 = 8 * 1024 * 1024;
+
+    /// Bytes of `destination` that are safe to resume from. When a journal
+    /// exists, the file is validated against it and truncated back to the
+    /// last verified checkpoint on mismatch; otherwise the on-disk size is
+    /// trusted as before.
+    pub async fn resumable_size(path: &Path, destination: &Path) -> u64 {
+        let disk_size = tokio::fs::metadata(destination)
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0);
+        let Some(mut record) = PartialDownload::load_from(path)
+            .await
+            .filter(|r| r.file_path == destination)
+        else {
+            return disk_size;
+        };
+
+        match record.verify(true).await {
+            Ok(report) => {
+                if report.repaired {
+                    log::warn!(
+                        "Partial download {:?} was {:?} ({} bytes on disk, {} journaled); resuming from {}",
+                        destination,
+                        report.status,
+                        report.disk_size,
+                        report.journal_size,
+                        report.verified_size
+                    );
+                    if let Err(e) = record.save_to(path).await {
+                        log::warn!("Failed to update download journal {:?}: {}", path, e);
+                    }
+                }
+                if report.status == PartialStatus::Missing {
+                    0
+                } else {
+                    report.verified_size
+                }
+            }
+            Err(e) => {
+                log::warn!(
+                    "Failed to validate partial download {:?}: {}",
+                    destination,
+                    e
+                );
+                0
+            }
+        }
+    }
+
+    /// Open (or start) the journal for `destination` with `offset` bytes
+    /// already durable in `file`.
+    pub async fn begin(
+        path: PathBuf,
+        url: &str,
+        destination: &Path,
+        file: &tokio::fs::File,
+        offset: u64,
+        expected_size: Option<u64>,
+    ) -> Self {
+        let mut record = PartialDownload::load_from(&path)
+            .await
+            .filter(|r| r.file_path == destination && offset > 0)
+            .unwrap_or_else(|| PartialDownload::new(url, destination.to_path_buf()));
+        record.expected_size = expected_size;
+        record.supports_resume = true;
+
+        let mut journal = Self {
+            path,
+            record,
+            last_checkpoint: offset,
+        };
+        journal.persist(file, offset).await;
+        journal
+    }
+
+    /// Whether enough new bytes were written to warrant a checkpoint
+    pub fn is_due(&self, offset: u64) -> bool {
+        offset.saturating_sub(self.last_checkpoint) >= Self::CHECKPOINT_BYTES
+    }
+
+    /// Flush and fsync `file`, then record `offset` as verified
+    pub async fn checkpoint(&mut self, file: &mut tokio::fs::File, offset: u64) {
+        use tokio::io::AsyncWriteExt;
+
+        if offset == self.last_checkpoint {
+            return;
+        }
+        if let Err(e) = file.flush().await {
+            log::warn!("Failed to flush {:?}: {}", self.record.file_path, e);
+            return;
+        }
+        self.persist(file, offset).await;
+    }
+
+    async fn persist(&mut self, file: &tokio::fs::File, offset: u64) {
+        let result = match self.record.checkpoint(file, offset).await {
+            Ok(()) => self.record.save_to(&self.path).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => self.last_checkpoint = offset,
+            Err(e) => log::warn!(
+                "Failed to checkpoint download journal {:?}: {}",
+                self.path,
+                e
+            ),
+        }
+    }
+
+    /// Drop the journal once the download finished or was discarded
+    pub async fn discard(path: &Path) {
+        let _ = tokio::fs::remove_file(path).await;
+    }
 }
 
 /// Builder for creating download tasks with fluent API
@@ -550,6 +768,7 @@ pub fn build(self) -> DownloadTask {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
 
     #[test]
     fn test_download_progress_new() {
@@ -730,6 +949,37 @@ fn test_download_task_update_progress() {
         assert_eq!(task.progress.speed, 100.0);
     }
 
+    #[test]
+    fn test_download_task_counts_only_received_bytes_per_attempt() {
+        let mut task = DownloadTask::new(
+            "https://example.com/file.zip".to_string(),
+            PathBuf::from("/tmp/file.zip"),
+            "Test File".to_string(),
+        );
+
+        // First attempt fails after 400 bytes
+        task.begin_attempt(0);
+        task.update_progress(400, Some(1000), 0.0);
+        task.settle_attempt();
+        assert_eq!(task.received_bytes, 400);
+
+        // Failing before any data arrives adds nothing
+        task.settle_attempt();
+        assert_eq!(task.received_bytes, 400);
+
+        // Resuming at 400 counts only the remaining 600
+        task.begin_attempt(400);
+        task.update_progress(1000, Some(1000), 0.0);
+        task.settle_attempt();
+        assert_eq!(task.received_bytes, 1000);
+
+        // A restart from zero refetches everything
+        task.begin_attempt(0);
+        task.update_progress(1000, Some(1000), 0.0);
+        task.settle_attempt();
+        assert_eq!(task.received_bytes, 2000);
+    }
+
     #[test]
     fn test_download_task_builder_with_config() {
         let config = DownloadConfig {
@@ -1104,6 +1354,25 @@ fn test_download_task_builder_with_mirrors() {
         assert_eq!(task.mirror_urls.len(), 2);
     }
 
+    #[test]
+    fn test_download_task_effective_url_and_usage_provider() {
+        let mut task = DownloadTask::new(
+            "https://primary.com/file.zip".to_string(),
+            PathBuf::from("/tmp/file.zip"),
+            "file.zip".to_string(),
+        );
+        assert_eq!(task.effective_url(), "https://primary.com/file.zip");
+        assert_eq!(task.usage_provider(), "direct");
+
+        task.metadata.insert(
+            EFFECTIVE_URL_METADATA_KEY.to_string(),
+            "https://mirror1.com/file.zip".to_string(),
+        );
+        task.provider = Some("github".to_string());
+        assert_eq!(task.effective_url(), "https://mirror1.com/file.zip");
+        assert_eq!(task.usage_provider(), "github");
+    }
+
     #[test]
     fn test_download_task_mirror_urls_serde_roundtrip() {
         let mut task = DownloadTask::new(
@@ -1173,6 +1442,67 @@ fn test_speed_tracker_ewma_smoothing() {
         assert!(s2 > s1);
     }
 
+    #[test]
+    fn test_speed_tracker_smoothing_is_interval_independent() {
+        let start = Instant::now();
+        // Both trackers see 1 MB/s, then 3 s at 2 MB/s: once in 100 ms
+        // steps, once in a single 3 s step.
+        let mut fine = SpeedTracker::new();
+        fine.prev_time = start;
+        fine.update_at(1_000_000, start + Duration::from_secs(1));
+        let mut coarse = SpeedTracker::new();
+        coarse.prev_time = start;
+        coarse.update_at(1_000_000, start + Duration::from_secs(1));
+
+        let mut fine_speed = 0.0;
+        for step in 1..=30u64 {
+            fine_speed = fine.update_at(
+                1_000_000 + step * 200_000,
+                start + Duration::from_secs(1) + Duration::from_millis(step * 100),
+            );
+        }
+        let coarse_speed = coarse.update_at(7_000_000, start + Duration::from_secs(4));
+
+        assert!(fine_speed > 1_000_000.0 && fine_speed < 2_000_000.0);
+        assert!((fine_speed - coarse_speed).abs() < 50_000.0);
+    }
+
+    #[test]
+    fn test_speed_tracker_decays_when_idle() {
+        let start = Instant::now();
+        let mut tracker = SpeedTracker::new();
+        tracker.prev_time = start;
+        let initial = tracker.update_at(1_000_000, start + Duration::from_secs(1));
+
+        let mut speed = initial;
+        for step in 1..=10u64 {
+            let next = tracker.update_at(1_000_000, start + Duration::from_secs(1 + step));
+            assert!(next < speed);
+            speed = next;
+        }
+        assert!(speed < initial * 0.05);
+    }
+
+    #[test]
+    fn test_speed_tracker_baseline_ignores_existing_bytes() {
+        let start = Instant::now();
+        let mut tracker = SpeedTracker::new().with_baseline(500_000_000);
+        tracker.prev_time = start;
+        let speed = tracker.update_at(501_000_000, start + Duration::from_secs(1));
+        assert!((speed - 1_000_000.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_download_progress_stalled_has_no_eta() {
+        let progress = DownloadProgress::new(500, Some(1000), 100.0);
+        assert_eq!(progress.eta_secs, Some(5));
+        assert!(!progress.stalled);
+
+        let stalled = progress.with_stalled(true);
+        assert!(stalled.stalled);
+        assert_eq!(stalled.eta_secs, None);
+    }
+
     #[test]
     fn test_speed_tracker_skips_tiny_intervals() {
         let mut tracker = SpeedTracker::new();
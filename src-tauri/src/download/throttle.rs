@@ -1,9 +1,16 @@
 //! Speed limiting for downloads using token bucket algorithm
 
+use once_cell::sync::Lazy;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 
+/// Limiter shared by the download manager and in-process installer downloads
+static SHARED: Lazy<SpeedLimiter> = Lazy::new(SpeedLimiter::new);
+
+/// How many turns concurrent `acquire` callers get per second of bandwidth
+const GRANT_SLICES_PER_SECOND: u64 = 20;
+
 /// Token bucket for rate limiting
 #[derive(Debug)]
 struct TokenBucket {
@@ -53,6 +60,11 @@ fn try_consume(&mut self, amount: u64) -> Option<u64> {
         }
     }
 
+    /// Largest grant handed to one `acquire` call, 1/20th of a second's worth
+    fn grant_slice(&self) -> u64 {
+        (self.rate / GRANT_SLICES_PER_SECOND).max(1)
+    }
+
     fn time_to_available(&self, amount: u64) -> Duration {
         if self.tokens >= amount {
             Duration::ZERO
@@ -92,6 +104,12 @@ pub fn with_limit(bytes_per_second: u64) -> Self {
         limiter
     }
 
+    /// The process-wide limiter, so queued downloads and the ones environment
+    /// installs make split the configured bandwidth between them
+    pub fn shared() -> Self {
+        SHARED.clone()
+    }
+
     /// Set the speed limit in bytes per second (0 to disable)
     pub fn set_limit(&self, bytes_per_second: u64) {
         let bucket = if bytes_per_second > 0 {
@@ -123,25 +141,29 @@ pub fn is_enabled(&self) -> bool {
 
     /// Acquire permission to transfer bytes, returns how many bytes can be transferred
     /// This will wait if necessary to respect the rate limit
+    ///
+    /// Each call is granted at most a slice of the rate and waits while holding
+    /// the bucket, so concurrent downloads queue on the lock and take turns
+    /// instead of one large transfer draining every token.
     pub async fn acquire(&self, requested_bytes: u64) -> u64 {
         if !self.is_enabled() {
             return requested_bytes;
         }
 
+        let mut guard = self.bucket.lock().await;
         loop {
-            let mut guard = self.bucket.lock().await;
             if let Some(ref mut bucket) = *guard {
-                if let Some(granted) = bucket.try_consume(requested_bytes) {
+                let wanted = requested_bytes.min(bucket.grant_slice());
+                if let Some(granted) = bucket.try_consume(wanted) {
                     return granted;
                 }
 
                 // Wait for tokens to become available
-                let wait_time = bucket.time_to_available(requested_bytes.min(bucket.capacity));
-                drop(guard);
-
-                if wait_time > Duration::ZERO {
-                    tokio::time::sleep(wait_time.min(Duration::from_millis(100))).await;
-                }
+                let wait_time = bucket.time_to_available(wanted);
+                tokio::time::sleep(
+                    wait_time.clamp(Duration::from_millis(1), Duration::from_millis(100)),
+                )
+                .await;
             } else {
                 return requested_bytes;
             }
@@ -288,6 +310,40 @@ fn test_token_bucket_consume_exact() {
         assert_eq!(bucket.tokens, 0);
     }
 
+    #[tokio::test]
+    async fn test_speed_limiter_grants_one_slice_per_call() {
+        let limiter = SpeedLimiter::with_limit(1000);
+        assert_eq!(limiter.acquire(1_000_000).await, 50);
+    }
+
+    #[tokio::test]
+    async fn test_speed_limiter_concurrent_callers_take_turns() {
+        let limiter = SpeedLimiter::with_limit(1000);
+        while limiter.try_acquire(1000).await.is_some() {}
+
+        let order = std::sync::Mutex::new(Vec::new());
+        let consume = |name: &'static str| {
+            let (limiter, order) = (&limiter, &order);
+            async move {
+                let mut remaining = 150;
+                while remaining > 0 {
+                    remaining -= limiter.acquire(remaining).await;
+                    order.lock().unwrap().push(name);
+                }
+            }
+        };
+        tokio::join!(consume("a"), consume("b"));
+
+        // Each needs at least three slices, so neither is done after four
+        let order = order.into_inner().unwrap();
+        assert!(order.len() >= 6);
+        assert!(
+            order[..4].windows(2).all(|pair| pair[0] != pair[1]),
+            "{:?}",
+            order
+        );
+    }
+
     #[tokio::test]
     async fn test_speed_limiter_default() {
         let limiter = SpeedLimiter::default();
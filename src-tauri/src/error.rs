@@ -20,6 +20,12 @@ pub enum CogniaError {
     #[error("Version not installed: {0}")]
     VersionNotInstalled(String),
 
+    #[error("Project not found: {0}")]
+    ProjectNotFound(String),
+
+    #[error("Not found: {0}")]
+    NotFound(String),
+
     #[error("Dependency resolution failed: {0}")]
     Resolution(String),
 
@@ -32,6 +38,9 @@ pub enum CogniaError {
     #[error("Checksum mismatch: expected {expected}, got {actual}")]
     ChecksumMismatch { expected: String, actual: String },
 
+    #[error("Rate limit exceeded for {provider}, resets in {retry_after} seconds")]
+    RateLimited { provider: String, retry_after: u64 },
+
     #[error("Download failed: {0}")]
     Download(String),
 
@@ -50,6 +59,19 @@ pub enum CogniaError {
     #[error("Platform not supported: {0}")]
     PlatformNotSupported(String),
 
+    #[error("Not supported: {0}")]
+    Unsupported(String),
+
+    #[error(
+        "{provider} does not support {operation} (supports: {})",
+        .capabilities.join(", ")
+    )]
+    UnsupportedOperation {
+        provider: String,
+        operation: String,
+        capabilities: Vec<String>,
+    },
+
     #[error("Permission denied: {0}")]
     PermissionDenied(String),
 
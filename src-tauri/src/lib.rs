@@ -12,7 +12,10 @@
 pub mod secrets;
 pub mod tray;
 
-use cache::{CleanupHistory, CleanupRecordBuilder, DownloadCache, DownloadResumer, MetadataCache};
+use cache::{
+    CleanupHistory, CleanupRecordBuilder, DownloadCache, DownloadHistory, DownloadResumer,
+    HistoryRetention, MetadataCache,
+};
 use commands::custom_detection::SharedCustomDetectionManager;
 #[cfg(not(test))]
 use commands::download::{setup_download_manager, SharedDownloadManager};
@@ -23,6 +26,12 @@
 #[cfg(not(test))]
 use core::custom_detection::CustomDetectionManager;
 #[cfg(not(test))]
+use core::launch_monitor::{LaunchMonitor, LaunchMonitorEvent, SharedLaunchMonitor};
+#[cfg(not(test))]
+use core::notifications::{NotificationCenter, SharedNotificationCenter};
+#[cfg(not(test))]
+use core::operation_queue::{OperationQueue, SharedOperationQueue};
+#[cfg(not(test))]
 use core::terminal::TerminalProfileManager;
 use log::{debug, info};
 use provider::ProviderRegistry;
@@ -165,6 +174,7 @@ pub fn run() {
     // Install panic hook BEFORE anything else so crashes generate reports
     commands::diagnostic::install_panic_hook();
     reset_startup_status();
+    core::metrics::mark_app_started();
 
     #[cfg(debug_assertions)]
     let devtools_plugin = tauri_plugin_devtools::init();
@@ -175,17 +185,19 @@ pub fn run() {
     // Skip it when a CLI subcommand is detected so headless commands run independently.
     #[cfg(desktop)]
     {
-        if !cli::has_cli_subcommand() {
+        if !cli::has_cli_subcommand() && !cli::is_background_update_check() {
             builder = builder.plugin(tauri_plugin_single_instance::init(|app, args, cwd| {
                 if let Some(window) = app.get_webview_window("main") {
                     let _ = window.show();
                     let _ = window.unminimize();
                     let _ = window.set_focus();
                 }
+                commands::deep_link::handle_args(app, &args);
                 let payload = serde_json::json!({ "args": args, "cwd": cwd });
                 let _ = app.emit("single-instance", payload);
             }));
         }
+        builder = builder.plugin(tauri_plugin_deep_link::init());
     }
 
     #[cfg(debug_assertions)]
@@ -261,6 +273,29 @@ pub fn run() {
             #[cfg(desktop)]
             app.handle().plugin(tauri_plugin_cli::init())?;
 
+            // cognia:// links: macOS delivers them to the deep-link plugin, Windows and
+            // Linux pass them as arguments (forwarded by single-instance when running)
+            #[cfg(desktop)]
+            {
+                use tauri_plugin_deep_link::DeepLinkExt;
+
+                #[cfg(any(windows, target_os = "linux"))]
+                {
+                    if let Err(e) = app.deep_link().register_all() {
+                        log::warn!("Failed to register cognia:// URL scheme: {}", e);
+                    }
+                }
+                let deep_link_app = app.handle().clone();
+                app.deep_link().on_open_url(move |event| {
+                    commands::deep_link::handle_urls(
+                        &deep_link_app,
+                        event.urls().into_iter().map(|url| url.to_string()),
+                    );
+                });
+                let args: Vec<String> = std::env::args().skip(1).collect();
+                commands::deep_link::handle_args(app.handle(), &args);
+            }
+
             // Register global shortcut plugin (frontend JS API handles registration)
             #[cfg(desktop)]
             app.handle().plugin(tauri_plugin_global_shortcut::Builder::new().build())?;
@@ -268,8 +303,20 @@ pub fn run() {
             // Initialize provider registry with defaults asynchronously
             let registry = app.state::<SharedRegistry>().inner().clone();
             let settings = app.state::<SharedSettings>().inner().clone();
+            let settings_load_report = app
+                .state::<commands::config::SharedSettingsLoadReport>()
+                .inner()
+                .clone();
             let tray_state = app.state::<SharedTrayState>().inner().clone();
             let custom_detection = app.state::<SharedCustomDetectionManager>().inner().clone();
+            let custom_env_types = app
+                .state::<commands::custom_env_type::SharedCustomEnvTypeManager>()
+                .inner()
+                .clone();
+            let workspaces = app
+                .state::<commands::workspace::SharedWorkspaceManager>()
+                .inner()
+                .clone();
 
             // Get config directory for custom detection rules
             let config_dir = app.path().app_config_dir().unwrap_or_default();
@@ -287,11 +334,14 @@ pub fn run() {
             // ═══════════════════════════════════════════════════════════════════
             tauri::async_runtime::block_on(async {
                 // 1. Load settings from disk
-                match Settings::load().await {
-                    Ok(loaded_settings) => {
+                match Settings::load_and_migrate().await {
+                    Ok((loaded_settings, report)) => {
                         let mut settings_guard = settings.write().await;
                         *settings_guard = loaded_settings;
                         info!("Settings loaded successfully");
+                        if !report.is_clean() {
+                            *settings_load_report.write().await = Some(report);
+                        }
                     }
                     Err(e) => {
                         info!("Using default settings: {}", e);
@@ -333,28 +383,19 @@ pub fn run() {
                 emit_init_progress(&app_handle_for_init, "settings", 15, "splash.loadingSettings");
 
                 // 2. Initialize the shared HTTP client with proxy/security settings
+                //    plus the user release asset rules and archive symlink mode
                 {
                     let settings_guard = settings.read().await;
                     platform::proxy::rebuild_shared_client(&settings_guard);
+                    download::set_user_asset_rules(download::AssetRules::compile(
+                        &settings_guard.general.asset_match_rules,
+                        &settings_guard.general.asset_exclude_patterns,
+                    ));
+                    core::archive::set_symlink_mode(settings_guard.general.extract_symlink_mode);
                 }
 
                 // 3. Ensure critical directories exist (fast filesystem ops)
-                {
-                    let s = settings.read().await;
-                    let dirs = [
-                        s.get_root_dir(),
-                        s.get_cache_dir(),
-                        s.get_environments_dir(),
-                        s.get_bin_dir(),
-                        s.get_state_dir(),
-                    ];
-                    drop(s);
-                    for dir in &dirs {
-                        if let Err(e) = platform::fs::create_dir_all(dir).await {
-                            info!("Directory ensure error {:?}: {}", dir, e);
-                        }
-                    }
-                }
+                commands::config::ensure_data_dirs(&settings).await;
 
                 // 4. Initialize provider registry (struct construction, no subprocess I/O)
                 let settings_guard = settings.read().await;
@@ -381,6 +422,13 @@ pub fn run() {
                 std::process::exit(exit_code);
             }
 
+            // Scheduled background update check: run headless, report, exit.
+            if cli::is_background_update_check() {
+                let exit_code =
+                    commands::update_schedule::run_background_update_check(app.handle());
+                std::process::exit(exit_code);
+            }
+
             // ═══════════════════════════════════════════════════════════════════
             // PRE-REGISTER MANAGERS as empty/default placeholders.
             // Commands will see empty state until background init completes.
@@ -391,6 +439,41 @@ pub fn run() {
             let terminal_mgr: SharedTerminalProfileManager = Arc::new(RwLock::new(TerminalProfileManager::empty()));
             app.manage(terminal_mgr.clone());
 
+            // Jobs are tracked in memory until the history store is attached
+            let job_queue: SharedOperationQueue = Arc::new(OperationQueue::new());
+            let job_event_app = app.handle().clone();
+            job_queue.set_listener(move |event| {
+                let _ = job_event_app.emit("job-updated", &event);
+                if let Some(notification) = install_failure_notification(&event) {
+                    let app = job_event_app.clone();
+                    tauri::async_runtime::spawn(async move {
+                        commands::notifications::notify(&app, notification).await;
+                    });
+                }
+            });
+            app.manage(job_queue.clone());
+
+            // Processes started via the launch commands, sampled for resource usage
+            let launch_monitor: SharedLaunchMonitor = Arc::new(LaunchMonitor::new());
+            let launch_event_app = app.handle().clone();
+            launch_monitor.set_listener(move |event| {
+                let _ = match event {
+                    LaunchMonitorEvent::Started(info) => {
+                        launch_event_app.emit("launch-started", &info)
+                    }
+                    LaunchMonitorEvent::Sample(stats) => {
+                        launch_event_app.emit("launch-resource-sample", &stats)
+                    }
+                    LaunchMonitorEvent::LimitExceeded(limit) => {
+                        launch_event_app.emit("launch-limit-exceeded", &limit)
+                    }
+                    LaunchMonitorEvent::Exited { launch_id } => {
+                        launch_event_app.emit("launch-exited", &launch_id)
+                    }
+                };
+            });
+            app.manage(launch_monitor);
+
             // PluginManager::new() is synchronous — safe to call here
             let plugin_deps = plugin::PluginDeps {
                 registry: registry.clone(),
@@ -410,9 +493,13 @@ pub fn run() {
             let bg_app = app.handle().clone();
             let bg_settings = settings.clone();
             let bg_custom_detection = custom_detection.clone();
+            let bg_custom_env_types = custom_env_types.clone();
+            let bg_registry = registry.clone();
+            let bg_workspaces = workspaces.clone();
             let bg_download = download_mgr.clone();
             let bg_terminal = terminal_mgr.clone();
             let bg_plugin = plugin_mgr.clone();
+            let bg_jobs = job_queue.clone();
             let bg_config_dir = config_dir.clone();
 
             tauri::async_runtime::spawn(async move {
@@ -484,6 +571,28 @@ pub fn run() {
                     } else {
                         info!("Custom detection rules loaded");
                     }
+                    drop(custom_detection_guard);
+
+                    let mut custom_env_types_guard = bg_custom_env_types.write().await;
+                    *custom_env_types_guard =
+                        core::custom_env_type::CustomEnvTypeManager::new(&bg_config_dir);
+                    if let Err(e) = custom_env_types_guard.load().await {
+                        info!("Custom environment types load error (using empty): {}", e);
+                    }
+                    drop(custom_env_types_guard);
+                    commands::custom_env_type::sync_custom_env_providers(
+                        &bg_custom_env_types,
+                        &bg_custom_detection,
+                        &bg_registry,
+                        &bg_settings,
+                    )
+                    .await;
+
+                    let mut workspaces_guard = bg_workspaces.write().await;
+                    *workspaces_guard = core::workspace::WorkspaceManager::new(&bg_config_dir);
+                    if let Err(e) = workspaces_guard.load().await {
+                        info!("Workspaces load error (using empty): {}", e);
+                    }
                 })
                 .await
                 .is_err()
@@ -515,6 +624,33 @@ pub fn run() {
                     emit_startup_status(&bg_app);
                 }
 
+                // ── Optional loopback /metrics listener (off by default) ──
+                commands::metrics::apply_metrics_listener(&bg_app).await;
+
+                // ── Optional read-only local JSON-RPC API (off by default) ──
+                commands::local_api::apply_local_api_server(&bg_app).await;
+
+                // ── Job history (marks jobs left running last session as interrupted) ──
+                if let Some(db_path) = OperationQueue::default_db_path() {
+                    match bg_jobs.attach_store(&db_path).await {
+                        Ok(interrupted) if !interrupted.is_empty() => {
+                            info!("{} job(s) were interrupted by the last shutdown", interrupted.len());
+                            let _ = bg_app.emit("jobs-interrupted", &interrupted);
+                        }
+                        Ok(_) => info!("Job history loaded"),
+                        Err(e) => info!("Job history unavailable (jobs kept in memory only): {}", e),
+                    }
+                }
+
+                // ── Notification history ──
+                if let Some(db_path) = NotificationCenter::default_db_path() {
+                    let center = bg_app.state::<SharedNotificationCenter>().inner().clone();
+                    match center.attach_store(&db_path).await {
+                        Ok(()) => commands::notifications::refresh_unread_count(&bg_app, &center).await,
+                        Err(e) => info!("Notification history unavailable (kept in memory only): {}", e),
+                    }
+                }
+
                 // ── Terminal profile manager (replaces pre-registered empty) ──
                 emit_init_progress(&bg_app, "terminal", 80, "splash.loadingTerminal");
                 if tokio::time::timeout(phase_timeout, async {
@@ -643,6 +779,9 @@ pub fn run() {
                 });
             }
 
+            // Keep the scheduled update check pointing at the current binary
+            tauri::async_runtime::spawn(commands::update_schedule::refresh_schedule_after_update());
+
             // Start background cache cleanup task
             let cleanup_settings = app.state::<SharedSettings>().inner().clone();
             let cleanup_app_handle = app.handle().clone();
@@ -650,6 +789,19 @@ pub fn run() {
                 cache_cleanup_task(cleanup_settings, cleanup_app_handle).await;
             });
 
+            // Move plaintext provider tokens into the secret store
+            {
+                let secret_settings = app.state::<SharedSettings>().inner().clone();
+                let secret_vault = app.state::<SharedSecretVault>().inner().clone();
+                tauri::async_runtime::spawn(async move {
+                    commands::secrets::migrate_plaintext_provider_secrets(
+                        secret_settings,
+                        secret_vault,
+                    )
+                    .await;
+                });
+            }
+
             // Start background auto-backup task
             {
                 let backup_settings = app.state::<SharedSettings>().inner().clone();
@@ -665,18 +817,34 @@ pub fn run() {
         })
         .manage(Arc::new(core::VersionCache::new(core::VERSION_CACHE_TTL)) as core::SharedVersionCache)
         .manage(Arc::new(core::eol::EolCache::new()) as commands::environment::SharedEolCache)
+        .manage(Arc::new(NotificationCenter::new()) as SharedNotificationCenter)
         .manage(Arc::new(RwLock::new(ProviderRegistry::new())) as SharedRegistry)
         .manage(Arc::new(RwLock::new(Settings::default())) as SharedSettings)
+        .manage(Arc::new(RwLock::new(None)) as commands::config::SharedSettingsLoadReport)
         .manage(Arc::new(RwLock::new(secrets::SecretVault::default())) as SharedSecretVault)
         .manage(Arc::new(RwLock::new(HashMap::new())) as CancellationTokens)
         .manage(Arc::new(RwLock::new(TrayState::default())) as SharedTrayState)
+        .manage(
+            Arc::new(std::sync::Mutex::new(Vec::new()))
+                as commands::deep_link::SharedPendingDeepLinks,
+        )
         .manage(Arc::new(RwLock::new(CustomDetectionManager::new(
             std::path::Path::new(""),
         ))) as SharedCustomDetectionManager)
+        .manage(Arc::new(RwLock::new(
+            core::custom_env_type::CustomEnvTypeManager::new(std::path::Path::new("")),
+        )) as commands::custom_env_type::SharedCustomEnvTypeManager)
+        .manage(Arc::new(RwLock::new(core::workspace::WorkspaceManager::new(
+            std::path::Path::new(""),
+        ))) as commands::workspace::SharedWorkspaceManager)
         .manage(core::create_shared_profile_manager(
             std::path::PathBuf::from(""),
             Arc::new(RwLock::new(ProviderRegistry::new())),
         ))
+        .manage(
+            Arc::new(core::terminal_session::TerminalSessionManager::new())
+                as core::terminal_session::SharedTerminalSessionManager,
+        )
         .invoke_handler(tauri::generate_handler![
             // Environment commands
             commands::environment::env_list,
@@ -688,9 +856,11 @@ pub fn run() {
             commands::environment::env_detect,
             commands::environment::env_detect_all,
             commands::environment::env_available_versions,
+            commands::environment::env_refresh_versions,
             commands::environment::env_list_providers,
             commands::environment::env_resolve_alias,
             commands::environment::env_install_cancel,
+            commands::environment::env_install_batch,
             commands::environment::env_save_settings,
             commands::environment::env_load_settings,
             commands::environment::env_detect_system_all,
@@ -698,6 +868,7 @@ pub fn run() {
             commands::environment::env_detect_system,
             commands::environment::env_get_type_mapping,
             commands::environment::env_verify_install,
+            commands::environment::env_smoke_test_approve,
             commands::environment::env_installed_versions,
             commands::environment::env_current_version,
             // Detection source commands
@@ -707,12 +878,21 @@ pub fn run() {
             // EOL commands
             commands::environment::env_get_eol_info,
             commands::environment::env_get_version_eol,
+            commands::environment::env_upgrade_advice,
+            commands::environment::env_explain_resolution,
             // Environment update checking & cleanup commands
             commands::environment::env_check_updates,
             commands::environment::env_check_updates_all,
+            commands::release_notes::release_notes_get,
             commands::environment::env_cleanup_versions,
+            commands::environment::env_validate_installs,
+            commands::environment::env_remove_broken,
+            commands::environment::env_disk_usage,
+            commands::environment::env_reclaim_suggestions,
             commands::environment::env_list_global_packages,
             commands::environment::env_migrate_packages,
+            commands::environment::env_update_global_packages,
+            commands::environment::env_node_package_managers,
             // Rustup-specific commands
             commands::environment::rustup_list_components,
             commands::environment::rustup_add_component,
@@ -730,15 +910,23 @@ pub fn run() {
             commands::environment::rustup_which,
             commands::environment::rustup_get_profile,
             commands::environment::rustup_set_profile,
+            commands::environment::rustup_toolchain_sizes,
+            commands::environment::rustup_gc,
+            commands::environment::import_from_version_managers,
             // Go-specific commands
             commands::environment::go_env_info,
             commands::environment::go_mod_tidy,
             commands::environment::go_mod_download,
+            commands::environment::go_work_info,
+            commands::environment::go_work_sync,
             commands::environment::go_clean_cache,
             commands::environment::go_cache_info,
+            commands::environment::dotnet_list_runtimes,
             // Package commands
             commands::package::package_search,
             commands::package::package_info,
+            commands::package::provider_cache_warm,
+            commands::package::provider_selftest,
             commands::package::package_install,
             commands::package::pre_install_validate,
             commands::package::package_uninstall,
@@ -754,19 +942,35 @@ pub fn run() {
             commands::package::provider_disable,
             commands::package::provider_set_priority,
             commands::package::resolve_dependency_conflict,
+            // Package try commands
+            commands::package_try::package_try,
+            commands::package_try::package_try_list,
+            commands::package_try::package_try_promote,
+            commands::package_try::package_try_remove,
             // Config commands
             commands::config::config_get,
             commands::config::config_set,
             commands::config::config_list,
             commands::config::config_list_defaults,
+            commands::config::config_take_load_report,
             commands::config::config_reset,
             commands::config::config_export,
             commands::config::config_import,
+            commands::config::config_import_preview,
+            commands::config::team_config_export,
+            commands::config::team_config_preview,
+            commands::config::team_config_apply,
+            commands::config::team_config_status,
             commands::secrets::secret_vault_status,
             commands::secrets::secret_vault_setup,
             commands::secrets::secret_vault_unlock,
             commands::secrets::secret_vault_lock,
             commands::secrets::secret_vault_reset,
+            commands::secrets::secret_store_status,
+            commands::secrets::secret_set,
+            commands::secrets::secret_get,
+            commands::secrets::secret_delete,
+            commands::secrets::secret_list,
             commands::config::detect_system_proxy,
             commands::config::test_proxy_connection,
             commands::config::get_cognia_dir,
@@ -776,12 +980,20 @@ pub fn run() {
             commands::config::get_components_info,
             commands::config::get_battery_info,
             commands::config::app_check_init,
+            // Onboarding commands
+            commands::onboarding::onboarding_status,
+            commands::onboarding::onboarding_complete_step,
+            commands::onboarding::onboarding_skip_step,
+            commands::onboarding::onboarding_reset,
             // Cache commands
             commands::cache::cache_info,
             commands::cache::cache_clean,
             commands::cache::cache_clean_preview,
             commands::cache::cache_clean_enhanced,
             commands::cache::cache_verify,
+            commands::cache::cache_quarantine_list,
+            commands::cache::cache_quarantine_restore,
+            commands::cache::cache_quarantine_purge,
             commands::cache::cache_repair,
             commands::cache::get_cache_settings,
             commands::cache::set_cache_settings,
@@ -791,6 +1003,8 @@ pub fn run() {
             // Cache access stats
             commands::cache::get_cache_access_stats,
             commands::cache::reset_cache_access_stats,
+            // Prometheus metrics export
+            commands::metrics::metrics_export,
             // Cache entry browser
             commands::cache::list_cache_entries,
             commands::cache::delete_cache_entry,
@@ -808,6 +1022,7 @@ pub fn run() {
             commands::cache::get_combined_cache_stats,
             // Cache size monitoring
             commands::cache::cache_size_monitor,
+            commands::cache::cleanup_analytics,
             // Cache path management
             commands::cache::get_cache_path_info,
             commands::cache::set_cache_path,
@@ -818,6 +1033,7 @@ pub fn run() {
             // Force clean
             commands::cache::cache_force_clean,
             commands::cache::cache_force_clean_external,
+            commands::cache::clean_external_cache_preview,
             // External cache paths
             commands::cache::get_external_cache_paths,
             // Scan configuration
@@ -840,6 +1056,9 @@ pub fn run() {
             commands::backup::backup_export,
             commands::backup::backup_import,
             commands::backup::backup_cleanup,
+            commands::backup::backup_full_create,
+            commands::backup::backup_inspect,
+            commands::backup::backup_full_restore,
             commands::backup::db_integrity_check,
             commands::backup::db_get_info,
             // Batch operations
@@ -847,6 +1066,7 @@ pub fn run() {
             commands::batch::batch_uninstall,
             commands::batch::batch_update,
             commands::batch::resolve_dependencies,
+            commands::batch::resolve_dependencies_explain,
             commands::batch::check_updates,
             commands::batch::package_pin,
             commands::batch::package_unpin,
@@ -861,6 +1081,10 @@ pub fn run() {
             commands::health_check::health_check_fix,
             commands::health_check::health_check_package_manager,
             commands::health_check::health_check_package_managers,
+            // Job commands
+            commands::jobs::jobs_list,
+            commands::jobs::jobs_get,
+            commands::jobs::jobs_cancel,
             // Profile commands
             commands::profiles::profile_list,
             commands::profiles::profile_get,
@@ -870,6 +1094,7 @@ pub fn run() {
             commands::profiles::profile_apply,
             commands::profiles::profile_export,
             commands::profiles::profile_import,
+            commands::profiles::profile_import_preview,
             commands::profiles::profile_create_from_current,
             commands::profiles::profile_capture_wsl_snapshot,
             commands::profiles::profile_apply_wsl_snapshot,
@@ -877,6 +1102,8 @@ pub fn run() {
             commands::search::advanced_search,
             commands::search::search_suggestions,
             commands::search::compare_packages,
+            commands::search::global_search,
+            commands::search::global_search_cancel,
             // Launch commands
             commands::launch::launch_with_env,
             commands::launch::launch_with_streaming,
@@ -884,18 +1111,38 @@ pub fn run() {
             commands::launch::env_get_info,
             commands::launch::exec_shell_with_env,
             commands::launch::which_program,
+            commands::launch::which_program_detailed,
+            commands::launch::launch_list_running,
+            commands::launch::launch_get_stats,
+            commands::launch::launch_stop,
+            commands::project_tasks::project_tasks_list,
+            commands::project_tasks::project_task_run,
+            // Local API commands
+            commands::local_api::api_server_status,
             // Shim commands
             commands::shim::shim_create,
             commands::shim::shim_remove,
             commands::shim::shim_list,
             commands::shim::shim_update,
             commands::shim::shim_regenerate_all,
+            commands::shim::shim_generate_completions,
+            // Stack commands
+            commands::stacks::stack_create_from_current,
+            commands::stacks::stack_export,
+            commands::stacks::stack_import,
+            commands::stacks::stack_preview,
+            commands::stacks::stack_apply,
+            // Trash commands
+            commands::trash::trash_list,
+            commands::trash::trash_restore,
+            commands::trash::trash_purge,
             // PATH commands
             commands::shim::path_status,
             commands::shim::path_setup,
             commands::shim::path_remove,
             commands::shim::path_check,
             commands::shim::path_get_add_command,
+            commands::shim::path_get_init_script,
             // Environment variable management commands
             commands::envvar::envvar_get_support_snapshot,
             commands::envvar::envvar_list_snapshots,
@@ -914,7 +1161,9 @@ pub fn run() {
             commands::envvar::envvar_get_persistent,
             commands::envvar::envvar_set_persistent,
             commands::envvar::envvar_remove_persistent,
+            commands::envvar::envvar_apply_batch,
             commands::envvar::envvar_get_path,
+            commands::envvar::path_shadow_report,
             commands::envvar::envvar_add_path_entry,
             commands::envvar::envvar_remove_path_entry,
             commands::envvar::envvar_reorder_path,
@@ -926,6 +1175,8 @@ pub fn run() {
             commands::envvar::envvar_export_env_file,
             commands::envvar::envvar_list_persistent,
             commands::envvar::envvar_expand,
+            commands::envvar::envvar_resolve_env_files,
+            commands::envvar::envvar_export_merged_env_file,
             commands::envvar::envvar_deduplicate_path,
             commands::envvar::envvar_preview_path_repair,
             commands::envvar::envvar_apply_path_repair,
@@ -937,9 +1188,17 @@ pub fn run() {
             // Updater commands
             commands::updater::self_check_update,
             commands::updater::self_update,
+            commands::updater::self_list_available_versions,
+            commands::update_schedule::schedule_update_check_enable,
+            commands::update_schedule::schedule_update_check_disable,
+            commands::update_schedule::schedule_update_check_status,
+            commands::update_schedule::schedule_update_check_last_report,
             // Log commands
             commands::log::log_list_files,
             commands::log::log_query,
+            commands::log::log_stats,
+            commands::log::log_follow_start,
+            commands::log::log_follow_stop,
             commands::log::log_clear,
             commands::log::log_get_dir,
             commands::log::log_export,
@@ -958,6 +1217,16 @@ pub fn run() {
             // Manifest commands
             commands::manifest::manifest_read,
             commands::manifest::manifest_init,
+            commands::manifest::manifest_diagnostics,
+            commands::manifest::manifest_format,
+            // Notification center commands
+            commands::notifications::notifications_list,
+            commands::notifications::notifications_mark_read,
+            commands::notifications::notifications_clear,
+            commands::notifications::notifications_get_unread_count,
+            // Deep link commands
+            commands::deep_link::deeplink_parse,
+            commands::deep_link::deeplink_take_pending,
             // Download commands
             commands::download::download_add,
             commands::download::download_get,
@@ -977,6 +1246,7 @@ pub fn run() {
             commands::download::download_set_max_concurrent,
             commands::download::download_get_max_concurrent,
             commands::download::download_verify_file,
+            commands::download::download_validate_partials,
             commands::download::download_open_file,
             commands::download::download_reveal_file,
             commands::download::download_batch_pause,
@@ -994,6 +1264,11 @@ pub fn run() {
             commands::download::download_history_stats,
             commands::download::download_history_clear,
             commands::download::download_history_remove,
+            commands::download::download_history_export,
+            commands::download::download_history_import,
+            // Bandwidth usage commands
+            commands::download::download_usage_stats,
+            commands::download::download_usage_reset,
             // Disk space commands
             commands::download::disk_space_get,
             commands::download::disk_space_check,
@@ -1007,6 +1282,7 @@ pub fn run() {
             commands::github::github_list_releases,
             commands::github::github_list_workflow_artifacts,
             commands::github::github_get_release_assets,
+            commands::github::github_pick_asset_explain,
             commands::github::github_download_asset,
             commands::github::github_download_source,
             commands::github::github_download_workflow_artifact,
@@ -1014,6 +1290,7 @@ pub fn run() {
             commands::github::github_get_token,
             commands::github::github_clear_token,
             commands::github::github_validate_token,
+            commands::github::github_rate_limit_status,
             // GitLab commands
             commands::gitlab::gitlab_parse_url,
             commands::gitlab::gitlab_validate_project,
@@ -1037,6 +1314,7 @@ pub fn run() {
             commands::gitlab::gitlab_download_package_file,
             commands::gitlab::gitlab_set_instance_url,
             commands::gitlab::gitlab_get_instance_url,
+            commands::gitlab::gitlab_rate_limit_status,
             // Window effect commands
             commands::window_effect::window_effect_apply,
             commands::window_effect::window_effect_clear,
@@ -1089,11 +1367,27 @@ pub fn run() {
             commands::custom_detection::custom_rule_detect,
             commands::custom_detection::custom_rule_detect_all,
             commands::custom_detection::custom_rule_test,
+            commands::custom_detection::custom_rule_approve_command,
             commands::custom_detection::custom_rule_validate_regex,
             commands::custom_detection::custom_rule_export,
             commands::custom_detection::custom_rule_import,
             commands::custom_detection::custom_rule_list_by_env,
             commands::custom_detection::custom_rule_extraction_types,
+            // Custom environment type commands
+            commands::custom_env_type::custom_env_type_list,
+            commands::custom_env_type::custom_env_type_get,
+            commands::custom_env_type::custom_env_type_validate,
+            commands::custom_env_type::custom_env_type_add,
+            commands::custom_env_type::custom_env_type_update,
+            commands::custom_env_type::custom_env_type_delete,
+            commands::custom_env_type::custom_env_type_approve_commands,
+            // Workspace commands
+            commands::workspace::workspace_list,
+            commands::workspace::workspace_create,
+            commands::workspace::workspace_delete,
+            commands::workspace::workspace_scan,
+            commands::workspace::workspace_status,
+            commands::workspace::workspace_apply_envs,
             // WSL commands
             commands::wsl::wsl_list_distros,
             commands::wsl::wsl_list_online,
@@ -1118,6 +1412,9 @@ pub fn run() {
             commands::wsl::wsl_get_config,
             commands::wsl::wsl_set_config,
             commands::wsl::wsl_set_networking_mode,
+            commands::wsl::wsl_config_schema,
+            commands::wsl::wsl_config_validate,
+            commands::wsl::wsl_config_set_key,
             commands::wsl::wsl_disk_usage,
             commands::wsl::wsl_import_in_place,
             commands::wsl::wsl_mount,
@@ -1143,12 +1440,14 @@ pub fn run() {
             commands::uv::uv_sync,
             commands::uv::uv_lock,
             commands::uv::uv_run,
+            commands::uv::uv_run_script,
             commands::uv::uv_tree,
             commands::uv::uv_venv_create,
             commands::uv::uv_python_install,
             commands::uv::uv_python_uninstall,
             commands::uv::uv_python_list,
             commands::uv::uv_python_pin,
+            commands::uv::uv_python_find,
             commands::uv::uv_pip_compile,
             commands::uv::uv_self_update,
             commands::uv::uv_version,
@@ -1158,6 +1457,33 @@ pub fn run() {
             commands::uv::uv_tool_uninstall,
             commands::uv::uv_tool_list,
             commands::uv::uv_tool_run,
+            commands::uv::uv_workspace_list,
+            commands::uv::uv_script_deps,
+            // Volta commands
+            commands::volta::volta_status,
+            commands::volta::volta_list,
+            commands::volta::volta_install,
+            commands::volta::volta_pin,
+            commands::volta::volta_which,
+            // FVM (Flutter) commands
+            commands::fvm::fvm_list_installed,
+            commands::fvm::fvm_list_releases,
+            commands::fvm::fvm_install,
+            commands::fvm::fvm_use,
+            commands::fvm::fvm_remove,
+            commands::fvm::fvm_doctor,
+            commands::fvm::fvm_project_status,
+            commands::fvm::fvm_install_script_preview,
+            commands::fvm::fvm_self_install,
+            // juliaup / rig commands
+            commands::juliaup::juliaup_self_install,
+            commands::rig::rig_self_install,
+            // SDKMAN commands
+            commands::sdkman::sdkman_install_script_preview,
+            commands::sdkman::sdkman_self_install,
+            // Mirror sync commands
+            commands::mirror_sync::mirror_sync_tools,
+            commands::mirror_sync::mirror_unsync_tools,
             // Conda environment management commands
             commands::conda::conda_env_list,
             commands::conda::conda_env_create,
@@ -1172,6 +1498,15 @@ pub fn run() {
             commands::conda::conda_config_set,
             commands::conda::conda_channel_add,
             commands::conda::conda_channel_remove,
+            commands::conda::conda_env_disk_usage,
+            commands::conda::conda_env_prune_preview,
+            commands::conda::conda_env_prune,
+            commands::conda::conda_env_diff,
+            commands::conda::conda_env_sync,
+            commands::conda::conda_lock_install,
+            // CUDA toolkit and driver commands
+            commands::cuda::cuda_list_installed,
+            commands::cuda::cuda_detect_compatibility,
             // Poetry project management commands
             commands::poetry::poetry_lock,
             commands::poetry::poetry_update,
@@ -1182,6 +1517,10 @@ pub fn run() {
             commands::poetry::poetry_export,
             commands::poetry::poetry_check,
             commands::poetry::poetry_version,
+            commands::poetry::poetry_add,
+            commands::poetry::poetry_remove,
+            commands::poetry::poetry_show,
+            commands::poetry::poetry_groups_list,
             // pipx commands
             commands::pipx::pipx_inject,
             commands::pipx::pipx_run,
@@ -1190,12 +1529,32 @@ pub fn run() {
             commands::pipx::pipx_ensurepath,
             commands::pipx::pipx_reinstall_all,
             commands::pipx::pipx_list_json,
+            // Python tool commands (uv / pipx)
+            commands::python_tools::python_tool_install,
+            commands::python_tools::python_tool_list,
+            commands::python_tools::python_tool_upgrade,
+            commands::python_tools::python_tool_uninstall,
+            commands::python_tools::python_tool_migrate,
+            // pixi commands
+            commands::pixi::pixi_project_init,
+            commands::pixi::pixi_add,
+            commands::pixi::pixi_remove,
+            commands::pixi::pixi_install,
+            commands::pixi::pixi_list,
+            commands::pixi::pixi_env_info,
+            commands::pixi::pixi_run,
+            commands::pixi::pixi_global_install,
+            commands::pixi::pixi_global_list,
+            commands::pixi::pixi_global_remove,
             // Xmake/Xrepo commands
             commands::xmake::xmake_list_repos,
             commands::xmake::xmake_add_repo,
             commands::xmake::xmake_remove_repo,
             commands::xmake::xmake_update_repos,
             commands::xmake::xmake_clean_cache,
+            commands::xmake::xmake_cache_stats,
+            commands::xmake::xmake_project_deps,
+            commands::xmake::xmake_project_deps_export,
             commands::xmake::xmake_env_show,
             commands::xmake::xmake_env_list,
             commands::xmake::xmake_env_bind,
@@ -1219,6 +1578,12 @@ pub fn run() {
             commands::brew::brew_get_config,
             commands::brew::brew_analytics_status,
             commands::brew::brew_analytics_toggle,
+            commands::brew::brew_cask_list,
+            commands::brew::brew_cask_info,
+            commands::brew::brew_cask_outdated,
+            commands::brew::brew_cask_install,
+            commands::brew::brew_cask_uninstall,
+            commands::brew::brew_cask_upgrade,
             // MacPorts commands
             commands::macports::macports_list_variants,
             commands::macports::macports_port_contents,
@@ -1239,6 +1604,9 @@ pub fn run() {
             commands::wsl::wsl_total_disk_usage,
             commands::wsl::wsl_batch_launch,
             commands::wsl::wsl_batch_terminate,
+            commands::wsl::wsl_batch_exec,
+            commands::wsl::wsl_batch_update,
+            commands::wsl::wsl_batch_export,
             commands::wsl::wsl_list_port_forwards,
             commands::wsl::wsl_add_port_forward,
             commands::wsl::wsl_remove_port_forward,
@@ -1392,6 +1760,10 @@ pub fn run() {
             // Git interactive rebase
             commands::git::git_get_rebase_todo_preview,
             commands::git::git_start_interactive_rebase,
+            commands::git::git_rebase_plan,
+            commands::git::git_rebase_execute,
+            commands::git::git_rebase_status,
+            commands::git::git_commit_amend,
             // Git bisect
             commands::git::git_bisect_start,
             commands::git::git_bisect_good,
@@ -1429,15 +1801,27 @@ pub fn run() {
             commands::terminal::terminal_set_default_profile,
             commands::terminal::terminal_launch_profile,
             commands::terminal::terminal_launch_profile_detailed,
+            commands::terminal::terminal_launch_history,
+            commands::terminal::terminal_relaunch,
+            commands::terminal::terminal_launch_history_pin,
             commands::terminal::terminal_get_proxy_env_vars,
+            commands::terminal::terminal_session_open,
+            commands::terminal::terminal_session_write,
+            commands::terminal::terminal_session_resize,
+            commands::terminal::terminal_session_close,
+            commands::terminal::terminal_session_list,
             commands::terminal::terminal_read_config,
             commands::terminal::terminal_backup_config,
             commands::terminal::terminal_backup_config_verified,
             commands::terminal::terminal_append_to_config,
             commands::terminal::terminal_append_to_config_verified,
+            commands::terminal::terminal_config_upsert_entry,
+            commands::terminal::terminal_config_remove_entry,
             commands::terminal::terminal_get_config_entries,
             commands::terminal::terminal_parse_config_content,
             commands::terminal::terminal_validate_config_content,
+            commands::terminal::shell_profile_analyze,
+            commands::terminal::shell_profile_apply_fix,
             commands::terminal::terminal_get_config_editor_metadata,
             commands::terminal::terminal_restore_config_snapshot,
             commands::terminal::terminal_ps_list_profiles,
@@ -1460,6 +1844,7 @@ pub fn run() {
             commands::terminal::terminal_ps_install_module,
             commands::terminal::terminal_ps_uninstall_module,
             commands::terminal::terminal_ps_update_module,
+            commands::terminal::terminal_ps_update_all_modules,
             commands::terminal::terminal_ps_find_module,
             commands::terminal::terminal_list_templates,
             commands::terminal::terminal_create_custom_template,
@@ -1482,12 +1867,17 @@ pub fn run() {
             commands::plugin::plugin_enable,
             commands::plugin::plugin_disable,
             commands::plugin::plugin_reload,
+            commands::plugin::plugin_dev_watch,
+            commands::plugin::plugin_dev_unwatch,
+            commands::plugin::plugin_dev_watch_list,
             commands::plugin::plugin_call_tool,
             commands::plugin::plugin_get_permissions,
             commands::plugin::plugin_get_permission_mode,
             commands::plugin::plugin_grant_permission,
             commands::plugin::plugin_revoke_permission,
             commands::plugin::plugin_get_data_dir,
+            commands::plugin::plugin_storage_inspect,
+            commands::plugin::plugin_storage_clear,
             commands::plugin::plugin_get_locales,
             commands::plugin::plugin_scaffold,
             commands::plugin::plugin_open_scaffold_folder,
@@ -1506,8 +1896,13 @@ pub fn run() {
             commands::plugin::plugin_export_data,
             commands::plugin::plugin_get_settings_schema,
             commands::plugin::plugin_get_settings_values,
+            commands::plugin::plugin_get_settings_resolved,
             commands::plugin::plugin_set_setting,
             commands::plugin::plugin_check_all_updates,
+            commands::plugin::plugin_check_updates,
+            commands::plugin::plugin_marketplace_refresh,
+            commands::plugin::plugin_marketplace_list,
+            commands::plugin::plugin_marketplace_install,
             commands::plugin::plugin_update_all,
             commands::plugin::toolbox_cancel_tool,
             // Built-in toolbox backend bridge commands
@@ -1530,6 +1925,20 @@ pub fn run() {
             commands::winget::winget_download,
             commands::winget::winget_get_info,
             commands::winget::winget_install_advanced,
+            commands::winget::winget_install_environment,
+            // Scoop-specific commands
+            commands::scoop::scoop_bucket_list,
+            commands::scoop::scoop_bucket_add,
+            commands::scoop::scoop_bucket_remove,
+            commands::scoop::scoop_cleanup,
+            commands::scoop::scoop_hold,
+            commands::scoop::scoop_unhold,
+            // Chocolatey-specific commands
+            commands::chocolatey::choco_source_list,
+            commands::chocolatey::choco_source_add,
+            commands::chocolatey::choco_source_remove,
+            commands::chocolatey::choco_pin_list,
+            commands::chocolatey::choco_pin,
         ])
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
@@ -1561,8 +1970,40 @@ pub fn run() {
                 }
             }
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                let closed = app
+                    .state::<core::terminal_session::SharedTerminalSessionManager>()
+                    .close_all();
+                if closed > 0 {
+                    info!("Closed {} terminal session(s) on exit", closed);
+                }
+                if let Some(launch_monitor) = app.try_state::<SharedLaunchMonitor>() {
+                    let terminate = tauri::async_runtime::block_on(
+                        app.state::<SharedSettings>().read(),
+                    )
+                    .terminal
+                    .terminate_launches_on_exit;
+                    if terminate {
+                        launch_monitor.terminate_all();
+                    } else {
+                        let untracked = launch_monitor.untrack_all();
+                        if untracked > 0 {
+                            info!("Left {} launched process(es) running on exit", untracked);
+                        }
+                    }
+                }
+                if let Some(plugin_mgr) = app.try_state::<SharedPluginManager>() {
+                    let stopped =
+                        tauri::async_runtime::block_on(plugin_mgr.write()).stop_all_dev_watches();
+                    if stopped > 0 {
+                        info!("Stopped {} plugin dev watch(es) on exit", stopped);
+                    }
+                }
+            }
+        });
 }
 
 #[cfg(test)]
@@ -1743,6 +2184,72 @@ async fn cache_cleanup_task(settings: SharedSettings, app: tauri::AppHandle) {
             }
         }
 
+        // Download history retention also runs independently of cache auto_clean
+        {
+            let retention = {
+                let s = settings.read().await;
+                HistoryRetention {
+                    max_age_days: s.general.download_history_max_age_days,
+                    max_records: s.general.download_history_max_records,
+                }
+            };
+            if let Ok(mut history) = DownloadHistory::open(&cache_dir).await {
+                match history
+                    .enforce_retention(retention, chrono::Utc::now())
+                    .await
+                {
+                    Ok(removed) if removed > 0 => {
+                        debug!("History retention: removed {} download records", removed);
+                    }
+                    Err(e) => {
+                        debug!("History retention error: {}", e);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // Expired try-prefixes are removed regardless of cache auto_clean
+        {
+            let root_dir = settings.read().await.get_root_dir();
+            match core::package_try::prune_expired_tries(&root_dir, chrono::Utc::now()).await {
+                Ok(expired) if !expired.is_empty() => {
+                    debug!("Removed {} expired package try prefixes", expired.len());
+                }
+                Err(e) => {
+                    debug!("Package try cleanup error: {}", e);
+                }
+                _ => {}
+            }
+        }
+
+        // Trashed objects past their retention are purged regardless of cache auto_clean
+        {
+            let (root_dir, retention_days) = {
+                let s = settings.read().await;
+                (s.get_root_dir(), s.general.trash_retention_days)
+            };
+            if retention_days > 0 {
+                let cutoff = chrono::Utc::now() - chrono::Duration::days(retention_days as i64);
+                match core::trash::purge(&root_dir, Some(cutoff)).await {
+                    Ok(purged) if purged > 0 => {
+                        debug!("Purged {} expired trash entries", purged);
+                    }
+                    Err(e) => {
+                        debug!("Trash purge error: {}", e);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // The monthly summary covers the previous month and is sent once
+        if settings.read().await.general.cache_monthly_summary {
+            if let Some(notification) = monthly_cleanup_notification(&cache_dir, max_size).await {
+                commands::notifications::notify(&app, notification).await;
+            }
+        }
+
         if !auto_clean {
             continue;
         }
@@ -1829,7 +2336,12 @@ async fn cache_cleanup_task(settings: SharedSettings, app: tauri::AppHandle) {
                     };
 
                     if evict_target > 0 {
-                        match download_cache.evict_to_size(evict_target).await {
+                        // Entries referenced by installed environments are only
+                        // evicted while the cache stays above the hard limit.
+                        match download_cache
+                            .evict_to_size_with_hard_limit(evict_target, max_size)
+                            .await
+                        {
                             Ok(count) if count > 0 => {
                                 debug!(
                                     "Auto-cleanup: evicted {} download entries (usage: {}, target: {})",
@@ -1919,6 +2431,98 @@ async fn cache_cleanup_task(settings: SharedSettings, app: tauri::AppHandle) {
     }
 }
 
+/// Notification for an install job that finished with an error
+#[cfg_attr(test, allow(dead_code))]
+fn install_failure_notification(
+    event: &core::operation_queue::JobEvent,
+) -> Option<core::notifications::Notification> {
+    use core::notifications::{Notification, NotificationCategory, NotificationSeverity};
+    use core::operation_queue::{JobKind, JobLifecycle, JobStatus};
+
+    let job = &event.job;
+    if event.lifecycle != JobLifecycle::Finished
+        || job.status != JobStatus::Failed
+        || !matches!(job.kind, JobKind::EnvInstall | JobKind::BatchInstall)
+    {
+        return None;
+    }
+    Some(
+        Notification::new(
+            NotificationCategory::InstallFailure,
+            NotificationSeverity::Error,
+            "Install Failed",
+            format!(
+                "{}: {}",
+                job.title,
+                job.error.as_deref().unwrap_or("unknown error")
+            ),
+        )
+        .with_payload(serde_json::json!({
+            "jobId": job.id,
+            "kind": job.kind,
+            "params": job.params,
+        })),
+    )
+}
+
+/// Summary of space reclaimed last month and current usage against the limit,
+/// or `None` once it has already been sent for that month
+#[cfg_attr(test, allow(dead_code))]
+async fn monthly_cleanup_notification(
+    cache_dir: &std::path::Path,
+    max_size: u64,
+) -> Option<core::notifications::Notification> {
+    use core::notifications::{Notification, NotificationCategory, NotificationSeverity};
+    use platform::disk::format_size;
+
+    let mut history = CleanupHistory::open(cache_dir).await.ok()?;
+    let summary = match history.take_monthly_summary(chrono::Utc::now()).await {
+        Ok(summary) => summary?,
+        Err(e) => {
+            debug!("Monthly cleanup summary error: {}", e);
+            return None;
+        }
+    };
+
+    let current_size = match DownloadCache::open(cache_dir).await {
+        Ok(download_cache) => download_cache
+            .stats()
+            .await
+            .map(|stats| stats.total_size)
+            .unwrap_or(0),
+        Err(_) => 0,
+    };
+    let usage = if max_size > 0 {
+        format!(
+            "{} of {} ({:.0}%)",
+            format_size(current_size),
+            format_size(max_size),
+            current_size as f64 / max_size as f64 * 100.0
+        )
+    } else {
+        format_size(current_size)
+    };
+
+    Some(
+        Notification::new(
+            NotificationCategory::System,
+            NotificationSeverity::Info,
+            "Monthly Cache Summary",
+            format!(
+                "{}: reclaimed {} in {} cleanups. Cache usage is {}.",
+                summary.month, summary.freed_human, summary.cleanups, usage
+            ),
+        )
+        .with_payload(serde_json::json!({
+            "month": summary.month,
+            "cleanups": summary.cleanups,
+            "freedBytes": summary.freed_bytes,
+            "currentSize": current_size,
+            "maxSize": max_size,
+        })),
+    )
+}
+
 /// Background task for automatic backups based on backup settings.
 #[cfg_attr(test, allow(dead_code))]
 async fn auto_backup_task(
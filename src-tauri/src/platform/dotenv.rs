@@ -0,0 +1,666 @@
+//! Layered `.env` file resolution.
+//!
+//! Parses dotenv files with the common quoting conventions, merges several
+//! layers (`.env`, `.env.local`, `.env.production`, ...) where later files win,
+//! and expands `${VAR}` references lazily so forward references and cycles can
+//! be handled.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{CogniaError, CogniaResult};
+
+/// One piece of a parsed value: literal text or a variable reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DotenvSegment {
+    Literal(String),
+    Reference {
+        name: String,
+        default: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DotenvEntry {
+    pub key: String,
+    pub segments: Vec<DotenvSegment>,
+    pub line: usize,
+}
+
+impl DotenvEntry {
+    /// Render the value without expanding references.
+    pub fn raw_value(&self) -> String {
+        render_segments(&self.segments, false)
+    }
+}
+
+/// A single `.env` layer, identified by its source (usually a file path).
+#[derive(Debug, Clone)]
+pub struct DotenvLayer {
+    pub source: String,
+    pub entries: Vec<DotenvEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DotenvProvenance {
+    pub key: String,
+    pub value: String,
+    pub source: String,
+    pub line: usize,
+    /// Earlier layers whose definition of this key was overridden.
+    pub overridden_sources: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ResolvedDotenv {
+    /// Final entries in first-definition order.
+    pub entries: Vec<DotenvProvenance>,
+    /// Segments of the winning definition for each key, used when exporting
+    /// without expansion so references survive the round trip.
+    pub segments: HashMap<String, Vec<DotenvSegment>>,
+}
+
+impl ResolvedDotenv {
+    pub fn variables(&self) -> HashMap<String, String> {
+        self.entries
+            .iter()
+            .map(|entry| (entry.key.clone(), entry.value.clone()))
+            .collect()
+    }
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+/// Parse dotenv content.
+///
+/// - `'single'` quoted values are literal and may span lines.
+/// - `"double"` quoted values may span lines and support `\n`, `\r`, `\t`,
+///   `\"`, `\\` and `\$` escapes plus `${VAR}` / `$VAR` references.
+/// - Unquoted values are trimmed, stop at an inline ` #` comment and support
+///   references.
+pub fn parse_dotenv(content: &str) -> CogniaResult<Vec<DotenvEntry>> {
+    let chars: Vec<char> = content.chars().collect();
+    let mut entries = Vec::new();
+    let mut pos = 0usize;
+    let mut line = 1usize;
+
+    while pos < chars.len() {
+        // Skip leading whitespace and blank lines
+        while pos < chars.len() && chars[pos].is_whitespace() {
+            if chars[pos] == '\n' {
+                line += 1;
+            }
+            pos += 1;
+        }
+        if pos >= chars.len() {
+            break;
+        }
+
+        let entry_line = line;
+        if chars[pos] == '#' {
+            skip_to_line_end(&chars, &mut pos);
+            continue;
+        }
+
+        let mut key = read_key(&chars, &mut pos);
+        if key == "export" && pos < chars.len() && (chars[pos] == ' ' || chars[pos] == '\t') {
+            skip_inline_whitespace(&chars, &mut pos);
+            key = read_key(&chars, &mut pos);
+        }
+        skip_inline_whitespace(&chars, &mut pos);
+
+        if key.is_empty() || pos >= chars.len() || chars[pos] != '=' {
+            return Err(CogniaError::Parse(format!(
+                "Invalid .env line {}: expected KEY=VALUE",
+                entry_line
+            )));
+        }
+        pos += 1;
+        skip_inline_whitespace(&chars, &mut pos);
+
+        let segments = match chars.get(pos) {
+            Some('\'') => {
+                pos += 1;
+                let value = read_until_quote(&chars, &mut pos, &mut line, '\'', entry_line)?;
+                skip_trailing(&chars, &mut pos, entry_line)?;
+                vec![DotenvSegment::Literal(value)]
+            }
+            Some('"') => {
+                pos += 1;
+                let segments = read_double_quoted(&chars, &mut pos, &mut line, entry_line)?;
+                skip_trailing(&chars, &mut pos, entry_line)?;
+                segments
+            }
+            _ => {
+                let start = pos;
+                while pos < chars.len() && chars[pos] != '\n' {
+                    if chars[pos] == '#' && pos > start && chars[pos - 1].is_whitespace() {
+                        break;
+                    }
+                    pos += 1;
+                }
+                let raw: String = chars[start..pos].iter().collect();
+                skip_to_line_end(&chars, &mut pos);
+                parse_references(raw.trim(), false)
+            }
+        };
+
+        entries.push(DotenvEntry {
+            key,
+            segments,
+            line: entry_line,
+        });
+    }
+
+    Ok(entries)
+}
+
+fn read_key(chars: &[char], pos: &mut usize) -> String {
+    let start = *pos;
+    while *pos < chars.len()
+        && (chars[*pos].is_ascii_alphanumeric() || matches!(chars[*pos], '_' | '.' | '-'))
+    {
+        *pos += 1;
+    }
+    chars[start..*pos].iter().collect()
+}
+
+fn skip_inline_whitespace(chars: &[char], pos: &mut usize) {
+    while *pos < chars.len() && (chars[*pos] == ' ' || chars[*pos] == '\t') {
+        *pos += 1;
+    }
+}
+
+fn skip_to_line_end(chars: &[char], pos: &mut usize) {
+    while *pos < chars.len() && chars[*pos] != '\n' {
+        *pos += 1;
+    }
+}
+
+/// After a closing quote only whitespace or a comment may follow.
+fn skip_trailing(chars: &[char], pos: &mut usize, entry_line: usize) -> CogniaResult<()> {
+    skip_inline_whitespace(chars, pos);
+    match chars.get(*pos) {
+        None | Some('\n') | Some('\r') | Some('#') => {
+            skip_to_line_end(chars, pos);
+            Ok(())
+        }
+        Some(_) => Err(CogniaError::Parse(format!(
+            "Invalid .env line {}: unexpected characters after closing quote",
+            entry_line
+        ))),
+    }
+}
+
+fn read_until_quote(
+    chars: &[char],
+    pos: &mut usize,
+    line: &mut usize,
+    quote: char,
+    entry_line: usize,
+) -> CogniaResult<String> {
+    let mut value = String::new();
+    while *pos < chars.len() {
+        let c = chars[*pos];
+        *pos += 1;
+        if c == quote {
+            return Ok(value);
+        }
+        if c == '\n' {
+            *line += 1;
+        }
+        value.push(c);
+    }
+    Err(CogniaError::Parse(format!(
+        "Invalid .env line {}: unterminated {} quote",
+        entry_line, quote
+    )))
+}
+
+fn read_double_quoted(
+    chars: &[char],
+    pos: &mut usize,
+    line: &mut usize,
+    entry_line: usize,
+) -> CogniaResult<Vec<DotenvSegment>> {
+    // Decode escapes first, marking escaped `$` with a sentinel so it is not
+    // treated as a reference afterwards.
+    let mut decoded = String::new();
+    while *pos < chars.len() {
+        let c = chars[*pos];
+        *pos += 1;
+        match c {
+            '"' => return Ok(parse_references(&decoded, true)),
+            '\\' if *pos < chars.len() => {
+                let next = chars[*pos];
+                *pos += 1;
+                match next {
+                    'n' => decoded.push('\n'),
+                    'r' => decoded.push('\r'),
+                    't' => decoded.push('\t'),
+                    '"' => decoded.push('"'),
+                    '\\' => decoded.push('\\'),
+                    '$' => decoded.push(ESCAPED_DOLLAR),
+                    '\n' => {
+                        // Backslash-newline is a line continuation
+                        *line += 1;
+                    }
+                    other => {
+                        decoded.push('\\');
+                        decoded.push(other);
+                    }
+                }
+            }
+            '\n' => {
+                *line += 1;
+                decoded.push('\n');
+            }
+            other => decoded.push(other),
+        }
+    }
+    Err(CogniaError::Parse(format!(
+        "Invalid .env line {}: unterminated \" quote",
+        entry_line
+    )))
+}
+
+const ESCAPED_DOLLAR: char = '\u{0}';
+
+fn parse_references(value: &str, has_escapes: bool) -> Vec<DotenvSegment> {
+    let chars: Vec<char> = value.chars().collect();
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut i = 0usize;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if has_escapes && c == ESCAPED_DOLLAR {
+            literal.push('$');
+            i += 1;
+            continue;
+        }
+        if c == '$' && i + 1 < chars.len() {
+            if chars[i + 1] == '{' {
+                if let Some(close) = chars[i + 2..].iter().position(|c| *c == '}') {
+                    let inner: String = chars[i + 2..i + 2 + close].iter().collect();
+                    let (name, default) = match inner.split_once(":-") {
+                        Some((name, default)) => (name.to_string(), Some(default.to_string())),
+                        None => (inner, None),
+                    };
+                    if is_valid_name(&name) {
+                        flush_literal(&mut segments, &mut literal);
+                        segments.push(DotenvSegment::Reference { name, default });
+                        i += close + 3;
+                        continue;
+                    }
+                }
+            } else if chars[i + 1] == '_' || chars[i + 1].is_ascii_alphabetic() {
+                let start = i + 1;
+                let mut end = start;
+                while end < chars.len() && (chars[end] == '_' || chars[end].is_ascii_alphanumeric())
+                {
+                    end += 1;
+                }
+                flush_literal(&mut segments, &mut literal);
+                segments.push(DotenvSegment::Reference {
+                    name: chars[start..end].iter().collect(),
+                    default: None,
+                });
+                i = end;
+                continue;
+            }
+        }
+        literal.push(c);
+        i += 1;
+    }
+
+    flush_literal(&mut segments, &mut literal);
+    segments
+}
+
+fn flush_literal(segments: &mut Vec<DotenvSegment>, literal: &mut String) {
+    if !literal.is_empty() {
+        segments.push(DotenvSegment::Literal(std::mem::take(literal)));
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c == '_' || c.is_ascii_alphabetic())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
+fn render_segments(segments: &[DotenvSegment], escape_dollar: bool) -> String {
+    let mut out = String::new();
+    for segment in segments {
+        match segment {
+            DotenvSegment::Literal(text) if escape_dollar => {
+                out.push_str(&text.replace('$', "\\$"))
+            }
+            DotenvSegment::Literal(text) => out.push_str(text),
+            DotenvSegment::Reference { name, default } => {
+                out.push_str("${");
+                out.push_str(name);
+                if let Some(default) = default {
+                    out.push_str(":-");
+                    out.push_str(default);
+                }
+                out.push('}');
+            }
+        }
+    }
+    out
+}
+
+// ============================================================================
+// Layering & expansion
+// ============================================================================
+
+/// Merge `layers` in order (later layers override earlier ones) and optionally
+/// expand references.
+///
+/// A reference resolves against the final merged value of that key, then
+/// `process_env`, then its `:-` default, then the empty string. A key that
+/// references itself (`PATH=${PATH}:/bin`) sees the definition it overrides,
+/// or the process env for the first definition. Cycles are reported with the
+/// full reference path.
+pub fn resolve_layers(
+    layers: &[DotenvLayer],
+    expand: bool,
+    process_env: &HashMap<String, String>,
+) -> CogniaResult<ResolvedDotenv> {
+    // Every definition of each key, in layer/line order
+    let mut definitions: HashMap<String, Vec<(&str, &DotenvEntry)>> = HashMap::new();
+    let mut order: Vec<String> = Vec::new();
+    for layer in layers {
+        for entry in &layer.entries {
+            let defs = definitions.entry(entry.key.clone()).or_default();
+            if defs.is_empty() {
+                order.push(entry.key.clone());
+            }
+            defs.push((layer.source.as_str(), entry));
+        }
+    }
+
+    let mut resolver = Resolver {
+        definitions: &definitions,
+        process_env,
+        cache: HashMap::new(),
+        stack: Vec::new(),
+    };
+
+    let mut entries = Vec::with_capacity(order.len());
+    let mut segments = HashMap::with_capacity(order.len());
+    for key in order {
+        let defs = &definitions[&key];
+        let last = defs.len() - 1;
+        let (source, entry) = defs[last];
+        let value = if expand {
+            resolver.resolve(&key, last)?
+        } else {
+            entry.raw_value()
+        };
+
+        let mut overridden_sources: Vec<String> = Vec::new();
+        for (earlier, _) in &defs[..last] {
+            if *earlier != source && !overridden_sources.iter().any(|s| s == earlier) {
+                overridden_sources.push(earlier.to_string());
+            }
+        }
+
+        segments.insert(key.clone(), entry.segments.clone());
+        entries.push(DotenvProvenance {
+            key,
+            value,
+            source: source.to_string(),
+            line: entry.line,
+            overridden_sources,
+        });
+    }
+
+    Ok(ResolvedDotenv { entries, segments })
+}
+
+struct Resolver<'a> {
+    definitions: &'a HashMap<String, Vec<(&'a str, &'a DotenvEntry)>>,
+    process_env: &'a HashMap<String, String>,
+    cache: HashMap<(String, usize), String>,
+    stack: Vec<(String, usize)>,
+}
+
+impl Resolver<'_> {
+    fn resolve(&mut self, key: &str, index: usize) -> CogniaResult<String> {
+        let id = (key.to_string(), index);
+        if let Some(value) = self.cache.get(&id) {
+            return Ok(value.clone());
+        }
+        if self.stack.contains(&id) {
+            let mut cycle: Vec<&str> = self
+                .stack
+                .iter()
+                .skip_while(|frame| **frame != id)
+                .map(|(name, _)| name.as_str())
+                .collect();
+            cycle.push(key);
+            return Err(CogniaError::Parse(format!(
+                "Circular variable reference: {}",
+                cycle.join(" -> ")
+            )));
+        }
+
+        self.stack.push(id.clone());
+        let entry = self.definitions[key][index].1;
+        let mut value = String::new();
+        for segment in &entry.segments {
+            match segment {
+                DotenvSegment::Literal(text) => value.push_str(text),
+                DotenvSegment::Reference { name, default } => {
+                    let target = if name == key {
+                        index.checked_sub(1)
+                    } else {
+                        self.definitions.get(name).map(|defs| defs.len() - 1)
+                    };
+                    let resolved = match target {
+                        Some(target) => Some(self.resolve(name, target)?),
+                        None => self.process_env.get(name).cloned(),
+                    };
+                    match (resolved, default) {
+                        (Some(resolved), Some(default)) if resolved.is_empty() => {
+                            value.push_str(default)
+                        }
+                        (Some(resolved), _) => value.push_str(&resolved),
+                        (None, Some(default)) => value.push_str(default),
+                        (None, None) => {}
+                    }
+                }
+            }
+        }
+        self.stack.pop();
+        self.cache.insert(id, value.clone());
+        Ok(value)
+    }
+}
+
+// ============================================================================
+// Rendering
+// ============================================================================
+
+/// Render a merged result back into a single dotenv file.
+///
+/// With `expanded` the resolved values are written literally (any `$` is
+/// escaped); otherwise the original references are preserved.
+pub fn render_dotenv(resolved: &ResolvedDotenv, expanded: bool) -> String {
+    let mut lines = Vec::with_capacity(resolved.entries.len());
+    for entry in &resolved.entries {
+        let value = if expanded {
+            entry.value.replace('$', "\\$")
+        } else {
+            resolved
+                .segments
+                .get(&entry.key)
+                .map(|segments| render_segments(segments, true))
+                .unwrap_or_default()
+        };
+        lines.push(format!("{}={}", entry.key, quote_value(&value)));
+    }
+    let mut content = lines.join("\n");
+    content.push('\n');
+    content
+}
+
+/// Quote a value (whose `$` handling is already done) for a dotenv file.
+fn quote_value(value: &str) -> String {
+    let needs_quotes = value.is_empty()
+        || value
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '#' | '\\'));
+    if !needs_quotes {
+        return value.to_string();
+    }
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            // Keep `\$` produced by the caller as a single escape
+            '\\' if chars.peek() == Some(&'$') => {
+                chars.next();
+                out.push_str("\\$");
+            }
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            other => out.push(other),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer(source: &str, content: &str) -> DotenvLayer {
+        DotenvLayer {
+            source: source.to_string(),
+            entries: parse_dotenv(content).unwrap(),
+        }
+    }
+
+    fn resolve(layers: &[DotenvLayer], env: &[(&str, &str)]) -> CogniaResult<ResolvedDotenv> {
+        let env = env
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        resolve_layers(layers, true, &env)
+    }
+
+    #[test]
+    fn test_parse_quoting_rules() {
+        let entries = parse_dotenv(
+            "A=plain value # comment\nB='single ${X} \\n'\nC=\"double\\nline \\$HOME\"\nexport D=x#y\n",
+        )
+        .unwrap();
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[0].raw_value(), "plain value");
+        assert_eq!(
+            entries[1].segments,
+            vec![DotenvSegment::Literal("single ${X} \\n".to_string())]
+        );
+        assert_eq!(
+            entries[2].segments,
+            vec![DotenvSegment::Literal("double\nline $HOME".to_string())]
+        );
+        assert_eq!(entries[3].key, "D");
+        assert_eq!(entries[3].raw_value(), "x#y");
+    }
+
+    #[test]
+    fn test_parse_multiline_and_line_numbers() {
+        let entries = parse_dotenv("A=\"one\ntwo\"\n\nB='x\ny'\nC=3").unwrap();
+        assert_eq!(entries[0].raw_value(), "one\ntwo");
+        assert_eq!(entries[1].line, 4);
+        assert_eq!(entries[1].raw_value(), "x\ny");
+        assert_eq!(entries[2].line, 6);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_dotenv("A=\"unterminated").is_err());
+        assert!(parse_dotenv("not a pair").is_err());
+        assert!(parse_dotenv("A='x' trailing").is_err());
+    }
+
+    #[test]
+    fn test_layers_override_with_provenance() {
+        let layers = [
+            layer(".env", "A=base\nB=keep"),
+            layer(".env.local", "A=local"),
+            layer(".env.production", "A=prod"),
+        ];
+        let resolved = resolve(&layers, &[]).unwrap();
+        let vars = resolved.variables();
+        assert_eq!(vars["A"], "prod");
+        assert_eq!(vars["B"], "keep");
+        let a = &resolved.entries[0];
+        assert_eq!(a.source, ".env.production");
+        assert_eq!(a.overridden_sources, vec![".env", ".env.local"]);
+    }
+
+    #[test]
+    fn test_expansion_uses_final_values_and_process_env() {
+        let layers = [
+            layer(
+                ".env",
+                "HOST=localhost\nURL=http://${HOST}:${PORT:-80}/$USER_NAME",
+            ),
+            layer(".env.local", "HOST=example.com"),
+        ];
+        let resolved = resolve(&layers, &[("USER_NAME", "me")]).unwrap();
+        assert_eq!(resolved.variables()["URL"], "http://example.com:80/me");
+    }
+
+    #[test]
+    fn test_self_reference_uses_previous_definition() {
+        let layers = [
+            layer(".env", "PATH=${PATH}:/a"),
+            layer(".env.local", "PATH=${PATH}:/b"),
+        ];
+        let resolved = resolve(&layers, &[("PATH", "/usr/bin")]).unwrap();
+        assert_eq!(resolved.variables()["PATH"], "/usr/bin:/a:/b");
+    }
+
+    #[test]
+    fn test_circular_reference_reports_path() {
+        let layers = [layer(".env", "A=${B}\nB=${C}\nC=${A}")];
+        let err = resolve(&layers, &[]).unwrap_err().to_string();
+        assert!(err.contains("A -> B -> C -> A"), "{}", err);
+    }
+
+    #[test]
+    fn test_render_round_trip() {
+        let layers = [layer(
+            ".env",
+            "A=\"multi\nline\"\nB=${A}-x\nC='$literal'\nD=plain",
+        )];
+        let env = HashMap::new();
+
+        let expanded = resolve_layers(&layers, true, &env).unwrap();
+        let rendered = render_dotenv(&expanded, true);
+        let reparsed = resolve_layers(&[layer("out", &rendered)], true, &env).unwrap();
+        assert_eq!(reparsed.variables(), expanded.variables());
+
+        let raw = resolve_layers(&layers, false, &env).unwrap();
+        let rendered = render_dotenv(&raw, false);
+        assert!(rendered.contains("B=${A}-x"));
+        let reparsed = resolve_layers(&[layer("out", &rendered)], true, &env).unwrap();
+        assert_eq!(reparsed.variables(), expanded.variables());
+    }
+}
@@ -391,6 +391,11 @@ pub fn get_path() -> Vec<PathBuf> {
 }
 
 pub fn expand_path(path: &str) -> String {
+    expand_path_with(path, &HashMap::new())
+}
+
+/// Like [`expand_path`], but `vars` take precedence over the process environment.
+pub fn expand_path_with(path: &str, vars: &HashMap<String, String>) -> String {
     let mut result = path.to_string();
 
     if result.starts_with('~') {
@@ -404,7 +409,10 @@ pub fn expand_path(path: &str) -> String {
         let re = regex::Regex::new(r"%([^%]+)%").unwrap();
         result = re
             .replace_all(&result, |caps: &regex::Captures| {
-                env::var(&caps[1]).unwrap_or_else(|_| caps[0].to_string())
+                vars.get(&caps[1])
+                    .cloned()
+                    .or_else(|| env::var(&caps[1]).ok())
+                    .unwrap_or_else(|| caps[0].to_string())
             })
             .to_string();
     }
@@ -414,7 +422,10 @@ pub fn expand_path(path: &str) -> String {
         let re = regex::Regex::new(r"\$\{?([A-Za-z_][A-Za-z0-9_]*)\}?").unwrap();
         result = re
             .replace_all(&result, |caps: &regex::Captures| {
-                env::var(&caps[1]).unwrap_or_else(|_| caps[0].to_string())
+                vars.get(&caps[1])
+                    .cloned()
+                    .or_else(|| env::var(&caps[1]).ok())
+                    .unwrap_or_else(|| caps[0].to_string())
             })
             .to_string();
     }
@@ -778,6 +789,40 @@ pub async fn get_persistent_path(scope: EnvVarScope) -> CogniaResult<Vec<String>
     }
 }
 
+/// Append PATH entries persisted since this process started (e.g. by an
+/// installer) to the process PATH, so newly installed tools resolve without a
+/// restart. Returns the entries added. Only Windows keeps a persistent PATH
+/// that installers write to; elsewhere this is a no-op.
+pub async fn refresh_process_path() -> CogniaResult<Vec<String>> {
+    if !cfg!(windows) {
+        return Ok(Vec::new());
+    }
+
+    let path_key = |entry: &str| entry.trim_end_matches(['\\', '/']).to_ascii_lowercase();
+    let mut current = get_path();
+    let mut known: std::collections::HashSet<String> = current
+        .iter()
+        .map(|entry| path_key(&entry.display().to_string()))
+        .collect();
+    let mut added = Vec::new();
+    for scope in [EnvVarScope::System, EnvVarScope::User] {
+        for entry in get_persistent_path(scope).await.unwrap_or_default() {
+            let expanded = expand_path(entry.trim());
+            if expanded.is_empty() || !known.insert(path_key(&expanded)) {
+                continue;
+            }
+            current.push(PathBuf::from(&expanded));
+            added.push(expanded);
+        }
+    }
+
+    if !added.is_empty() {
+        let joined = env::join_paths(&current).map_err(|e| CogniaError::Internal(e.to_string()))?;
+        env::set_var("PATH", joined);
+    }
+    Ok(added)
+}
+
 pub async fn set_persistent_path(entries: &[String], scope: EnvVarScope) -> CogniaResult<()> {
     match scope {
         EnvVarScope::Process => {
@@ -792,6 +837,743 @@ pub async fn set_persistent_path(entries: &[String], scope: EnvVarScope) -> Cogn
     }
 }
 
+// ============================================================================
+// Persistent env var transactions
+// ============================================================================
+
+const PATH_LIST_SEPARATOR: &str = if cfg!(windows) { ";" } else { ":" };
+
+/// A single staged change in an [`EnvVarTransaction`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum EnvVarOperation {
+    Set {
+        key: String,
+        value: String,
+    },
+    Remove {
+        key: String,
+    },
+    PathAdd {
+        entry: String,
+        #[serde(default)]
+        prepend: bool,
+    },
+    PathRemove {
+        entry: String,
+    },
+}
+
+/// Outcome of an applied [`EnvVarTransaction`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvVarTransactionResult {
+    pub scope: EnvVarScope,
+    /// Variables whose stored value changed, in apply order
+    pub changed_keys: Vec<String>,
+    /// Final PATH entries when the transaction edited PATH
+    pub path_entries: Option<Vec<String>>,
+}
+
+/// Stages several persistent env var edits and applies them as one unit.
+///
+/// Current values are snapshotted before anything is written; if a write
+/// fails, the changes already applied are restored in reverse order. On
+/// Windows `WM_SETTINGCHANGE` is broadcast once at the end. On Unix each
+/// touched profile file is backed up once and rewritten once.
+#[derive(Debug, Clone)]
+pub struct EnvVarTransaction {
+    scope: EnvVarScope,
+    operations: Vec<EnvVarOperation>,
+}
+
+impl EnvVarTransaction {
+    pub fn new(scope: EnvVarScope) -> Self {
+        Self {
+            scope,
+            operations: Vec::new(),
+        }
+    }
+
+    pub fn with_operations(scope: EnvVarScope, operations: Vec<EnvVarOperation>) -> Self {
+        Self { scope, operations }
+    }
+
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        self.operations.push(EnvVarOperation::Set {
+            key: key.into(),
+            value: value.into(),
+        });
+        self
+    }
+
+    pub fn remove(&mut self, key: impl Into<String>) -> &mut Self {
+        self.operations
+            .push(EnvVarOperation::Remove { key: key.into() });
+        self
+    }
+
+    pub fn path_add(&mut self, entry: impl Into<String>, prepend: bool) -> &mut Self {
+        self.operations.push(EnvVarOperation::PathAdd {
+            entry: entry.into(),
+            prepend,
+        });
+        self
+    }
+
+    pub fn path_remove(&mut self, entry: impl Into<String>) -> &mut Self {
+        self.operations.push(EnvVarOperation::PathRemove {
+            entry: entry.into(),
+        });
+        self
+    }
+
+    pub fn scope(&self) -> EnvVarScope {
+        self.scope
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    pub async fn apply(&self) -> CogniaResult<EnvVarTransactionResult> {
+        let staged = StagedEnvChanges::from_operations(&self.operations)?;
+        match self.scope {
+            EnvVarScope::Process => {
+                let mut store = ProcessEnvStore;
+                let (planned, path_entries) = plan_store_changes(&store, &staged, "PATH")?;
+                apply_planned_changes(&mut store, &planned)?;
+                Ok(EnvVarTransactionResult {
+                    scope: self.scope,
+                    changed_keys: planned.into_iter().map(|change| change.key).collect(),
+                    path_entries,
+                })
+            }
+            EnvVarScope::User | EnvVarScope::System => {
+                apply_transaction_platform(self.scope, &staged).await
+            }
+        }
+    }
+}
+
+pub(crate) fn is_path_key(key: &str) -> bool {
+    key.eq_ignore_ascii_case("PATH")
+}
+
+/// Whether a PATH entry refers to the inherited PATH (`$PATH`, `${PATH}`,
+/// `$env:PATH`, `%PATH%`) rather than naming a directory.
+pub(crate) fn is_inherited_path_reference(entry: &str) -> bool {
+    let lower = entry.to_ascii_lowercase();
+    ["$path", "${path}", "$env:path", "$env.path", "%path%"]
+        .iter()
+        .any(|reference| lower.contains(reference))
+}
+
+/// Operations folded into the final desired state.
+#[derive(Debug, Default)]
+struct StagedEnvChanges {
+    /// Desired value per variable (`None` removes it), in first-touched order
+    vars: Vec<(String, Option<String>)>,
+    path_edits: Vec<EnvVarOperation>,
+}
+
+impl StagedEnvChanges {
+    fn from_operations(operations: &[EnvVarOperation]) -> CogniaResult<Self> {
+        let mut staged = Self::default();
+        for operation in operations {
+            match operation {
+                EnvVarOperation::Set { key, value } => {
+                    staged.stage_var(key, Some(value.clone()))?
+                }
+                EnvVarOperation::Remove { key } => staged.stage_var(key, None)?,
+                EnvVarOperation::PathAdd { entry, .. } | EnvVarOperation::PathRemove { entry } => {
+                    if entry.trim().is_empty() {
+                        return Err(CogniaError::Config(
+                            "PATH entry must not be empty".to_string(),
+                        ));
+                    }
+                    staged.path_edits.push(operation.clone());
+                }
+            }
+        }
+        Ok(staged)
+    }
+
+    fn stage_var(&mut self, key: &str, value: Option<String>) -> CogniaResult<()> {
+        let key = normalize_env_var_key(key)?;
+        if is_path_key(&key) {
+            return Err(CogniaError::Config(
+                "Use path_add / path_remove operations to edit PATH".to_string(),
+            ));
+        }
+        match self.vars.iter_mut().find(|(staged, _)| *staged == key) {
+            Some(existing) => existing.1 = value,
+            None => self.vars.push((key, value)),
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn split_path_list(value: &str) -> Vec<String> {
+    value
+        .split(PATH_LIST_SEPARATOR)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| entry.to_string())
+        .collect()
+}
+
+/// Apply staged PATH edits to the current entries. Entries are kept verbatim,
+/// so unexpanded references such as `%USERPROFILE%\bin` survive the round trip.
+fn apply_path_edits(mut entries: Vec<String>, edits: &[EnvVarOperation]) -> Vec<String> {
+    let same = |a: &str, b: &str| {
+        if cfg!(windows) {
+            a.eq_ignore_ascii_case(b)
+        } else {
+            a == b
+        }
+    };
+    for edit in edits {
+        match edit {
+            EnvVarOperation::PathAdd { entry, prepend } => {
+                // Profiles already append the inherited PATH themselves
+                if is_inherited_path_reference(entry)
+                    || entries.iter().any(|existing| same(existing, entry))
+                {
+                    continue;
+                }
+                if *prepend {
+                    entries.insert(0, entry.clone());
+                } else {
+                    entries.push(entry.clone());
+                }
+            }
+            EnvVarOperation::PathRemove { entry } => {
+                entries.retain(|existing| !same(existing, entry));
+            }
+            EnvVarOperation::Set { .. } | EnvVarOperation::Remove { .. } => {}
+        }
+    }
+    entries
+}
+
+/// A persistent value as the platform stores it. `expand` marks Windows
+/// `REG_EXPAND_SZ` values, whose `%VAR%` references are expanded on read.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct StoredEnvValue {
+    value: String,
+    expand: bool,
+}
+
+impl StoredEnvValue {
+    /// Keep `REG_EXPAND_SZ` when the variable already had it, when it is PATH,
+    /// or when the new value references other variables.
+    fn for_write(key: &str, value: String, before: Option<&StoredEnvValue>) -> Self {
+        let expand =
+            is_path_key(key) || before.is_some_and(|before| before.expand) || value.contains('%');
+        Self { value, expand }
+    }
+}
+
+/// Key/value backend a transaction is applied against.
+trait EnvVarStore {
+    fn read(&self, key: &str) -> CogniaResult<Option<StoredEnvValue>>;
+    fn write(&mut self, key: &str, value: &StoredEnvValue) -> CogniaResult<()>;
+    fn delete(&mut self, key: &str) -> CogniaResult<()>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct PlannedEnvChange {
+    key: String,
+    before: Option<StoredEnvValue>,
+    after: Option<StoredEnvValue>,
+}
+
+/// Snapshot the current values and compute the writes needed, skipping no-ops.
+fn plan_store_changes(
+    store: &impl EnvVarStore,
+    staged: &StagedEnvChanges,
+    path_key: &str,
+) -> CogniaResult<(Vec<PlannedEnvChange>, Option<Vec<String>>)> {
+    let mut planned = Vec::new();
+    for (key, value) in &staged.vars {
+        let before = store.read(key)?;
+        let after = value
+            .clone()
+            .map(|value| StoredEnvValue::for_write(key, value, before.as_ref()));
+        if before != after {
+            planned.push(PlannedEnvChange {
+                key: key.clone(),
+                before,
+                after,
+            });
+        }
+    }
+
+    let mut path_entries = None;
+    if !staged.path_edits.is_empty() {
+        let before = store.read(path_key)?;
+        let current = before
+            .as_ref()
+            .map(|stored| split_path_list(&stored.value))
+            .unwrap_or_default();
+        let entries = apply_path_edits(current.clone(), &staged.path_edits);
+        if entries != current {
+            let after = StoredEnvValue::for_write(
+                path_key,
+                entries.join(PATH_LIST_SEPARATOR),
+                before.as_ref(),
+            );
+            planned.push(PlannedEnvChange {
+                key: path_key.to_string(),
+                before,
+                after: Some(after),
+            });
+        }
+        path_entries = Some(entries);
+    }
+
+    Ok((planned, path_entries))
+}
+
+/// Apply planned changes in order; on failure restore the applied ones newest first.
+fn apply_planned_changes(
+    store: &mut impl EnvVarStore,
+    planned: &[PlannedEnvChange],
+) -> CogniaResult<()> {
+    for (index, change) in planned.iter().enumerate() {
+        let outcome = match &change.after {
+            Some(value) => store.write(&change.key, value),
+            None => store.delete(&change.key),
+        };
+        if let Err(err) = outcome {
+            let mut rollback_failures = Vec::new();
+            for applied in planned[..index].iter().rev() {
+                let restored = match &applied.before {
+                    Some(value) => store.write(&applied.key, value),
+                    None => store.delete(&applied.key),
+                };
+                if let Err(rollback_err) = restored {
+                    rollback_failures.push(format!("{}: {}", applied.key, rollback_err));
+                }
+            }
+            return Err(transaction_error(
+                &change.key,
+                err,
+                index,
+                rollback_failures,
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn transaction_error(
+    target: &str,
+    err: CogniaError,
+    rolled_back: usize,
+    rollback_failures: Vec<String>,
+) -> CogniaError {
+    let mut message = format!(
+        "Failed to apply {}: {}; rolled back {} change(s)",
+        target, err, rolled_back
+    );
+    if !rollback_failures.is_empty() {
+        message.push_str(&format!(
+            "; rollback failed for {}",
+            rollback_failures.join(", ")
+        ));
+    }
+    match err {
+        CogniaError::PermissionDenied(_) => CogniaError::PermissionDenied(message),
+        _ => CogniaError::Internal(message),
+    }
+}
+
+struct ProcessEnvStore;
+
+impl EnvVarStore for ProcessEnvStore {
+    fn read(&self, key: &str) -> CogniaResult<Option<StoredEnvValue>> {
+        Ok(env::var(key).ok().map(|value| StoredEnvValue {
+            value,
+            expand: false,
+        }))
+    }
+
+    fn write(&mut self, key: &str, value: &StoredEnvValue) -> CogniaResult<()> {
+        env::set_var(key, &value.value);
+        Ok(())
+    }
+
+    fn delete(&mut self, key: &str) -> CogniaResult<()> {
+        env::remove_var(key);
+        Ok(())
+    }
+}
+
+#[cfg(windows)]
+struct RegistryEnvStore {
+    key: winreg::RegKey,
+}
+
+#[cfg(windows)]
+impl RegistryEnvStore {
+    fn open(scope: EnvVarScope, write: bool) -> CogniaResult<Self> {
+        Ok(Self {
+            key: open_env_regkey(scope, write)?,
+        })
+    }
+}
+
+#[cfg(windows)]
+impl EnvVarStore for RegistryEnvStore {
+    fn read(&self, key: &str) -> CogniaResult<Option<StoredEnvValue>> {
+        use winreg::enums::RegType;
+
+        match self.key.get_raw_value(key) {
+            Ok(raw) => {
+                let expand = match raw.vtype {
+                    RegType::REG_EXPAND_SZ => true,
+                    RegType::REG_SZ => false,
+                    _ => return Ok(None),
+                };
+                let value = String::from_utf16_lossy(
+                    &raw.bytes
+                        .chunks_exact(2)
+                        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                        .collect::<Vec<u16>>(),
+                )
+                .trim_end_matches('\0')
+                .to_string();
+                Ok(Some(StoredEnvValue { value, expand }))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(map_registry_io_error("Registry read failed", e)),
+        }
+    }
+
+    fn write(&mut self, key: &str, value: &StoredEnvValue) -> CogniaResult<()> {
+        use winreg::enums::RegType;
+
+        let mut bytes: Vec<u8> = value
+            .value
+            .encode_utf16()
+            .flat_map(|c| c.to_le_bytes())
+            .collect();
+        bytes.push(0);
+        bytes.push(0);
+        let reg_value = winreg::RegValue {
+            vtype: if value.expand {
+                RegType::REG_EXPAND_SZ
+            } else {
+                RegType::REG_SZ
+            },
+            bytes,
+        };
+        self.key
+            .set_raw_value(key, &reg_value)
+            .map_err(|e| map_registry_io_error("Registry write failed", e))
+    }
+
+    fn delete(&mut self, key: &str) -> CogniaResult<()> {
+        match self.key.delete_value(key) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(map_registry_io_error("Registry delete failed", e)),
+        }
+    }
+}
+
+/// Apply every planned change from one elevated process, so a transaction
+/// raises a single UAC prompt. The elevated script restores the values it
+/// already wrote if a later write fails.
+#[cfg(windows)]
+fn apply_planned_changes_elevated(planned: &[PlannedEnvChange]) -> CogniaResult<()> {
+    let payload = serde_json::to_vec(&serde_json::json!({ "changes": planned }))
+        .map_err(|e| CogniaError::Internal(e.to_string()))?;
+    let changes_file = elevation_temp_path("json");
+    std::fs::write(&changes_file, payload).map_err(CogniaError::Io)?;
+
+    let outcome = run_windows_env_operation_elevated(ElevatedWindowsEnvAction::Batch {
+        changes_file: &changes_file,
+    });
+    let _ = std::fs::remove_file(&changes_file);
+    outcome
+}
+
+#[cfg(windows)]
+async fn apply_transaction_platform(
+    scope: EnvVarScope,
+    staged: &StagedEnvChanges,
+) -> CogniaResult<EnvVarTransactionResult> {
+    let (planned, path_entries) =
+        plan_store_changes(&RegistryEnvStore::open(scope, false)?, staged, "Path")?;
+
+    if !planned.is_empty() {
+        let outcome = match RegistryEnvStore::open(scope, true) {
+            Ok(mut store) => apply_planned_changes(&mut store, &planned),
+            Err(CogniaError::PermissionDenied(_)) if scope == EnvVarScope::System => {
+                apply_planned_changes_elevated(&planned)
+            }
+            Err(err) => Err(err),
+        };
+        broadcast_env_change();
+        outcome?;
+    }
+
+    Ok(EnvVarTransactionResult {
+        scope,
+        changed_keys: planned.into_iter().map(|change| change.key).collect(),
+        path_entries,
+    })
+}
+
+/// One profile file rewritten by a Unix transaction.
+#[cfg(not(windows))]
+#[derive(Debug, Clone)]
+struct FileRewrite {
+    path: PathBuf,
+    original: Option<String>,
+    updated: String,
+}
+
+#[cfg(not(windows))]
+impl FileRewrite {
+    fn is_noop(&self) -> bool {
+        self.original.as_deref().unwrap_or("") == self.updated
+    }
+}
+
+#[cfg(not(windows))]
+fn env_backup_path(path: &Path) -> PathBuf {
+    let mut name = path
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_default();
+    name.push(".cognia-bak");
+    path.with_file_name(name)
+}
+
+#[cfg(not(windows))]
+fn map_profile_write_error(path: &Path, err: std::io::Error) -> CogniaError {
+    if err.kind() == std::io::ErrorKind::PermissionDenied {
+        CogniaError::PermissionDenied(format!("Cannot write {}: {}", path.display(), err))
+    } else {
+        CogniaError::Io(err)
+    }
+}
+
+/// Write a profile through a temp file and rename, so a failed write never
+/// leaves it truncated. A symlinked profile (dotfile managers) is written at
+/// its target, and the file's mode is kept.
+#[cfg(not(windows))]
+async fn write_profile_atomic(path: &Path, contents: &str) -> std::io::Result<()> {
+    let target = tokio::fs::canonicalize(path)
+        .await
+        .unwrap_or_else(|_| path.to_path_buf());
+    let permissions = tokio::fs::metadata(&target)
+        .await
+        .ok()
+        .map(|meta| meta.permissions());
+    crate::platform::fs::write_file_atomic(&target, contents.as_bytes())
+        .await
+        .map_err(|e| match e {
+            crate::platform::fs::FsError::Io(e) => e,
+            other => std::io::Error::other(other.to_string()),
+        })?;
+    if let Some(permissions) = permissions {
+        tokio::fs::set_permissions(&target, permissions).await?;
+    }
+    Ok(())
+}
+
+/// Back up and rewrite each file once; on failure restore every file
+/// touched so far, the failed one included, newest first.
+#[cfg(not(windows))]
+async fn apply_file_rewrites(rewrites: &[FileRewrite]) -> CogniaResult<()> {
+    for (index, rewrite) in rewrites.iter().enumerate() {
+        if let Some(original) = &rewrite.original {
+            let backup = env_backup_path(&rewrite.path);
+            if let Err(e) = tokio::fs::write(&backup, original).await {
+                let err = map_profile_write_error(&backup, e);
+                return Err(rollback_file_rewrites(&rewrites[..index], &rewrite.path, err).await);
+            }
+        }
+        if let Err(e) = write_profile_atomic(&rewrite.path, &rewrite.updated).await {
+            let err = map_profile_write_error(&rewrite.path, e);
+            return Err(rollback_file_rewrites(&rewrites[..=index], &rewrite.path, err).await);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(windows))]
+async fn rollback_file_rewrites(
+    applied: &[FileRewrite],
+    failed_path: &Path,
+    err: CogniaError,
+) -> CogniaError {
+    let mut rollback_failures = Vec::new();
+    for rewrite in applied.iter().rev() {
+        let restored = match &rewrite.original {
+            Some(original) => tokio::fs::write(&rewrite.path, original).await,
+            // The failed rewrite may never have created its file
+            None if !rewrite.path.exists() => Ok(()),
+            None => tokio::fs::remove_file(&rewrite.path).await,
+        };
+        if let Err(e) = restored {
+            rollback_failures.push(format!("{}: {}", rewrite.path.display(), e));
+        }
+    }
+    transaction_error(
+        &failed_path.display().to_string(),
+        err,
+        applied.len(),
+        rollback_failures,
+    )
+}
+
+/// Replace a shell profile's content, keeping a `.cognia-bak` copy of the
+/// previous version. Returns `false` when the content is already up to date.
+#[cfg(not(windows))]
+pub async fn rewrite_shell_profile(path: &Path, updated: String) -> CogniaResult<bool> {
+    let rewrite = FileRewrite {
+        path: path.to_path_buf(),
+        original: read_optional_file(path).await?,
+        updated,
+    };
+    if rewrite.is_noop() {
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| map_profile_write_error(parent, e))?;
+    }
+    apply_file_rewrites(std::slice::from_ref(&rewrite)).await?;
+    Ok(true)
+}
+
+#[cfg(not(windows))]
+async fn apply_transaction_platform(
+    scope: EnvVarScope,
+    staged: &StagedEnvChanges,
+) -> CogniaResult<EnvVarTransactionResult> {
+    let mut rewrites = Vec::new();
+    let mut changed_keys = Vec::new();
+    let mut path_entries = None;
+
+    match scope {
+        EnvVarScope::User => {
+            let shell = ShellType::detect();
+            let rc_path = shell.config_file().ok_or_else(|| {
+                CogniaError::Internal("Cannot determine shell config file".into())
+            })?;
+            let original = read_optional_file(&rc_path).await?;
+            let mut content = original.clone().unwrap_or_default();
+
+            for (key, value) in &staged.vars {
+                if find_var_in_shell_content(&content, key, &shell) == *value {
+                    continue;
+                }
+                content = match value {
+                    Some(value) => upsert_shell_var_content(&content, key, value, &shell),
+                    None => remove_shell_var_content(&content, key, &shell),
+                };
+                changed_keys.push(key.clone());
+            }
+
+            if !staged.path_edits.is_empty() {
+                let current = shell_path_entries(&content, &shell);
+                let entries = apply_path_edits(current.clone(), &staged.path_edits);
+                if entries != current {
+                    content = if entries.is_empty() {
+                        remove_shell_var_content(&content, "PATH", &shell)
+                    } else {
+                        upsert_shell_var_content(
+                            &content,
+                            "PATH",
+                            &format!("{}:$PATH", entries.join(":")),
+                            &shell,
+                        )
+                    };
+                    changed_keys.push("PATH".to_string());
+                }
+                path_entries = Some(entries);
+            }
+
+            rewrites.push(FileRewrite {
+                path: rc_path,
+                original,
+                updated: content,
+            });
+        }
+        EnvVarScope::System => {
+            let etc_env_path = system_environment_path();
+            let original = read_optional_file(&etc_env_path).await?;
+            let mut content = original.clone().unwrap_or_default();
+
+            for (key, value) in &staged.vars {
+                if etc_environment_value(&content, key) == *value {
+                    continue;
+                }
+                content = match value {
+                    Some(value) => upsert_etc_environment_content(&content, key, value),
+                    None => remove_etc_environment_content(&content, key),
+                };
+                changed_keys.push(key.clone());
+            }
+
+            if !staged.path_edits.is_empty() {
+                #[cfg(target_os = "macos")]
+                {
+                    let paths_file = PathBuf::from("/etc/paths.d/cognia");
+                    let current = get_persistent_path_platform(EnvVarScope::System).await?;
+                    let entries = apply_path_edits(current.clone(), &staged.path_edits);
+                    if entries != current {
+                        rewrites.push(FileRewrite {
+                            original: read_optional_file(&paths_file).await?,
+                            path: paths_file,
+                            updated: entries.join("\n") + "\n",
+                        });
+                        changed_keys.push("PATH".to_string());
+                    }
+                    path_entries = Some(entries);
+                }
+                #[cfg(not(target_os = "macos"))]
+                {
+                    let current = etc_environment_path_entries(&content);
+                    let entries = apply_path_edits(current.clone(), &staged.path_edits);
+                    if entries != current {
+                        content =
+                            upsert_etc_environment_content(&content, "PATH", &entries.join(":"));
+                        changed_keys.push("PATH".to_string());
+                    }
+                    path_entries = Some(entries);
+                }
+            }
+
+            rewrites.insert(
+                0,
+                FileRewrite {
+                    path: etc_env_path,
+                    original,
+                    updated: content,
+                },
+            );
+        }
+        EnvVarScope::Process => unreachable!(),
+    }
+
+    rewrites.retain(|rewrite| !rewrite.is_noop());
+    apply_file_rewrites(&rewrites).await?;
+
+    Ok(EnvVarTransactionResult {
+        scope,
+        changed_keys,
+        path_entries,
+    })
+}
+
 // ============================================================================
 // List persistent vars (cross-platform)
 // ============================================================================
@@ -1232,6 +2014,7 @@ enum ElevatedWindowsEnvAction<'a> {
     SetVar { key: &'a str, value: &'a str },
     RemoveVar { key: &'a str },
     SetPath { value: &'a str },
+    Batch { changes_file: &'a Path },
 }
 
 #[cfg(windows)]
@@ -1241,14 +2024,16 @@ fn operation(&self) -> &'static str {
             Self::SetVar { .. } => "set",
             Self::RemoveVar { .. } => "remove",
             Self::SetPath { .. } => "set_path",
+            Self::Batch { .. } => "batch",
         }
     }
 
-    fn key(&self) -> &str {
+    fn key(&self) -> Option<&str> {
         match self {
-            Self::SetVar { key, .. } => key,
-            Self::RemoveVar { key } => key,
-            Self::SetPath { .. } => "Path",
+            Self::SetVar { key, .. } => Some(key),
+            Self::RemoveVar { key } => Some(key),
+            Self::SetPath { .. } => Some("Path"),
+            Self::Batch { .. } => None,
         }
     }
 
@@ -1256,21 +2041,45 @@ fn value(&self) -> Option<&str> {
         match self {
             Self::SetVar { value, .. } => Some(value),
             Self::SetPath { value } => Some(value),
-            Self::RemoveVar { .. } => None,
+            Self::RemoveVar { .. } | Self::Batch { .. } => None,
+        }
+    }
+
+    fn changes_file(&self) -> Option<&Path> {
+        match self {
+            Self::Batch { changes_file } => Some(changes_file),
+            _ => None,
         }
     }
 }
 
+/// Unique temp file for handing data to the elevated script.
+#[cfg(windows)]
+fn elevation_temp_path(extension: &str) -> PathBuf {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    env::temp_dir().join(format!(
+        "cognia-env-elevate-{}-{}.{}",
+        std::process::id(),
+        unique,
+        extension
+    ))
+}
+
 #[cfg(windows)]
 const WINDOWS_ENV_ELEVATION_SCRIPT: &str = r#"
 param(
     [Parameter(Mandatory=$true)]
-    [ValidateSet('set','remove','set_path')]
+    [ValidateSet('set','remove','set_path','batch')]
     [string]$Operation,
-    [Parameter(Mandatory=$true)]
     [string]$Key,
     [AllowEmptyString()]
     [string]$Value,
+    [string]$Batch,
     [switch]$Elevated
 )
 
@@ -1282,14 +2091,19 @@ fn value(&self) -> Option<&str> {
             '-NoProfile',
             '-NonInteractive',
             '-ExecutionPolicy', 'Bypass',
-            '-File', $PSCommandPath,
+            '-File', ('"{0}"' -f $PSCommandPath),
             '-Elevated',
-            '-Operation', $Operation,
-            '-Key', $Key
+            '-Operation', $Operation
         )
+        if ($PSBoundParameters.ContainsKey('Key')) {
+            $argList += @('-Key', $Key)
+        }
         if ($PSBoundParameters.ContainsKey('Value')) {
             $argList += @('-Value', $Value)
         }
+        if ($PSBoundParameters.ContainsKey('Batch')) {
+            $argList += @('-Batch', ('"{0}"' -f $Batch))
+        }
 
         $proc = Start-Process -FilePath 'powershell.exe' -Verb RunAs -ArgumentList $argList -Wait -PassThru
         if ($null -eq $proc) {
@@ -1310,6 +2124,33 @@ fn value(&self) -> Option<&str> {
     'set'      { [Environment]::SetEnvironmentVariable($Key, $Value, $target) }
     'remove'   { [Environment]::SetEnvironmentVariable($Key, $null, $target) }
     'set_path' { [Environment]::SetEnvironmentVariable('Path', $Value, $target) }
+    'batch'    {
+        # Written straight to the registry so REG_EXPAND_SZ values keep their type
+        $envKey = [Microsoft.Win32.Registry]::LocalMachine.OpenSubKey(
+            'SYSTEM\CurrentControlSet\Control\Session Manager\Environment', $true)
+        function Write-EnvValue($name, $stored) {
+            if ($null -eq $stored) {
+                $envKey.DeleteValue($name, $false)
+            } elseif ($stored.expand) {
+                $envKey.SetValue($name, $stored.value, [Microsoft.Win32.RegistryValueKind]::ExpandString)
+            } else {
+                $envKey.SetValue($name, $stored.value, [Microsoft.Win32.RegistryValueKind]::String)
+            }
+        }
+        $payload = ConvertFrom-Json (Get-Content -Raw -LiteralPath $Batch)
+        $applied = New-Object System.Collections.ArrayList
+        try {
+            foreach ($change in $payload.changes) {
+                Write-EnvValue $change.key $change.after
+                [void]$applied.Insert(0, $change)
+            }
+        } catch {
+            foreach ($change in $applied) {
+                try { Write-EnvValue $change.key $change.before } catch {}
+            }
+            throw
+        }
+    }
     default    { throw "Unsupported operation: $Operation" }
 }
 "#;
@@ -1317,17 +2158,8 @@ fn value(&self) -> Option<&str> {
 #[cfg(windows)]
 fn run_windows_env_operation_elevated(action: ElevatedWindowsEnvAction<'_>) -> CogniaResult<()> {
     use std::process::Command;
-    use std::time::{SystemTime, UNIX_EPOCH};
 
-    let unique = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|d| d.as_nanos())
-        .unwrap_or_default();
-    let script_path = env::temp_dir().join(format!(
-        "cognia-env-elevate-{}-{}.ps1",
-        std::process::id(),
-        unique
-    ));
+    let script_path = elevation_temp_path("ps1");
 
     std::fs::write(&script_path, WINDOWS_ENV_ELEVATION_SCRIPT).map_err(CogniaError::Io)?;
 
@@ -1340,13 +2172,17 @@ fn run_windows_env_operation_elevated(action: ElevatedWindowsEnvAction<'_>) -> C
         .arg("-File")
         .arg(&script_path)
         .arg("-Operation")
-        .arg(action.operation())
-        .arg("-Key")
-        .arg(action.key());
+        .arg(action.operation());
 
+    if let Some(key) = action.key() {
+        command.arg("-Key").arg(key);
+    }
     if let Some(value) = action.value() {
         command.arg("-Value").arg(value);
     }
+    if let Some(changes_file) = action.changes_file() {
+        command.arg("-Batch").arg(changes_file);
+    }
 
     let output = command.output().map_err(|e| {
         CogniaError::Internal(format!(
@@ -1436,17 +2272,7 @@ async fn get_persistent_var_platform(
             let content = tokio::fs::read_to_string(system_environment_path())
                 .await
                 .unwrap_or_default();
-            for line in content.lines() {
-                let trimmed = line.trim();
-                if let Some(eq_pos) = trimmed.find('=') {
-                    let k = trimmed[..eq_pos].trim();
-                    if k == key {
-                        let v = trimmed[eq_pos + 1..].trim();
-                        return Ok(Some(strip_quotes(v).to_string()));
-                    }
-                }
-            }
-            Ok(None)
+            Ok(etc_environment_value(&content, key))
         }
         EnvVarScope::User => {
             let shell = ShellType::detect();
@@ -1464,6 +2290,48 @@ async fn get_persistent_var_platform(
     }
 }
 
+#[cfg(not(windows))]
+fn etc_environment_value(content: &str, key: &str) -> Option<String> {
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if let Some(eq_pos) = trimmed.find('=') {
+            let k = trimmed[..eq_pos].trim();
+            if k == key {
+                let v = trimmed[eq_pos + 1..].trim();
+                return Some(strip_quotes(v).to_string());
+            }
+        }
+    }
+    None
+}
+
+#[cfg(all(not(windows), not(target_os = "macos")))]
+fn etc_environment_path_entries(content: &str) -> Vec<String> {
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if let Some(value) = trimmed.strip_prefix("PATH=") {
+            return strip_quotes(value)
+                .split(':')
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+                .collect();
+        }
+    }
+    Vec::new()
+}
+
+/// PATH entries added by the shell profile, without the inherited `$PATH` reference
+#[cfg(not(windows))]
+fn shell_path_entries(content: &str, shell: &ShellType) -> Vec<String> {
+    let Some(val) = find_var_in_shell_content(content, "PATH", shell) else {
+        return Vec::new();
+    };
+    val.split(':')
+        .filter(|s| !s.is_empty() && !is_inherited_path_reference(s))
+        .map(|s| s.to_string())
+        .collect()
+}
+
 #[cfg(not(windows))]
 fn find_var_in_shell_content(content: &str, key: &str, shell: &ShellType) -> Option<String> {
     for line in content.lines() {
@@ -1673,18 +2541,7 @@ async fn get_persistent_path_platform(scope: EnvVarScope) -> CogniaResult<Vec<St
             #[cfg(target_os = "linux")]
             {
                 if let Ok(content) = tokio::fs::read_to_string(system_environment_path()).await {
-                    for line in content.lines() {
-                        let trimmed = line.trim();
-                        if trimmed.starts_with("PATH=") {
-                            let val = strip_quotes(&trimmed[5..]);
-                            entries.extend(
-                                val.split(':')
-                                    .filter(|s| !s.is_empty())
-                                    .map(|s| s.to_string()),
-                            );
-                            break;
-                        }
-                    }
+                    entries.extend(etc_environment_path_entries(&content));
                 }
             }
             Ok(entries)
@@ -1696,20 +2553,7 @@ async fn get_persistent_path_platform(scope: EnvVarScope) -> CogniaResult<Vec<St
                     let content = tokio::fs::read_to_string(&rc_path)
                         .await
                         .map_err(CogniaError::Io)?;
-                    if let Some(val) = find_var_in_shell_content(&content, "PATH", &shell) {
-                        let sep = ':';
-                        let parts: Vec<String> = val
-                            .split(sep)
-                            .filter(|s| {
-                                !s.is_empty()
-                                    && !s.contains("$PATH")
-                                    && !s.contains("${PATH}")
-                                    && !s.contains("$env.PATH")
-                            })
-                            .map(|s| s.to_string())
-                            .collect();
-                        return Ok(parts);
-                    }
+                    return Ok(shell_path_entries(&content, &shell));
                 }
             }
             Ok(Vec::new())
@@ -1791,6 +2635,17 @@ async fn set_persistent_path_platform(entries: &[String], scope: EnvVarScope) ->
 // Unix shell rc file helpers
 // ============================================================================
 
+#[cfg(not(windows))]
+async fn read_optional_file(path: &Path) -> CogniaResult<Option<String>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    tokio::fs::read_to_string(path)
+        .await
+        .map(Some)
+        .map_err(CogniaError::Io)
+}
+
 #[cfg(not(windows))]
 async fn upsert_shell_rc_var(
     rc_path: &Path,
@@ -1798,14 +2653,17 @@ async fn upsert_shell_rc_var(
     value: &str,
     shell: &ShellType,
 ) -> CogniaResult<()> {
-    let content = if rc_path.exists() {
-        tokio::fs::read_to_string(rc_path)
-            .await
-            .map_err(CogniaError::Io)?
-    } else {
-        String::new()
-    };
+    let content = read_optional_file(rc_path).await?.unwrap_or_default();
+    tokio::fs::write(
+        rc_path,
+        upsert_shell_var_content(&content, key, value, shell),
+    )
+    .await
+    .map_err(CogniaError::Io)
+}
 
+#[cfg(not(windows))]
+fn upsert_shell_var_content(content: &str, key: &str, value: &str, shell: &ShellType) -> String {
     let (pattern, new_line) = match shell {
         ShellType::Fish => {
             let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
@@ -1854,21 +2712,21 @@ async fn upsert_shell_rc_var(
     if !output.ends_with('\n') {
         output.push('\n');
     }
-    tokio::fs::write(rc_path, output)
-        .await
-        .map_err(CogniaError::Io)
+    output
 }
 
 #[cfg(not(windows))]
 async fn remove_shell_rc_var(rc_path: &Path, key: &str, shell: &ShellType) -> CogniaResult<()> {
-    if !rc_path.exists() {
+    let Some(content) = read_optional_file(rc_path).await? else {
         return Ok(());
-    }
-
-    let content = tokio::fs::read_to_string(rc_path)
+    };
+    tokio::fs::write(rc_path, remove_shell_var_content(&content, key, shell))
         .await
-        .map_err(CogniaError::Io)?;
+        .map_err(CogniaError::Io)
+}
 
+#[cfg(not(windows))]
+fn remove_shell_var_content(content: &str, key: &str, shell: &ShellType) -> String {
     let patterns: Vec<String> = match shell {
         ShellType::Fish => vec![format!("set -gx {} ", key)],
         ShellType::Nushell => vec![format!("$env.{} = ", key), format!("$env.{} =", key)],
@@ -1888,23 +2746,22 @@ async fn remove_shell_rc_var(rc_path: &Path, key: &str, shell: &ShellType) -> Co
     if !output.ends_with('\n') && !output.is_empty() {
         output.push('\n');
     }
-    tokio::fs::write(rc_path, output)
-        .await
-        .map_err(CogniaError::Io)
+    output
 }
 
 #[cfg(not(windows))]
 async fn upsert_etc_environment(key: &str, value: &str) -> CogniaResult<()> {
     let etc_env_path = system_environment_path();
-    let etc_env = etc_env_path.as_path();
-    let content = if etc_env.exists() {
-        tokio::fs::read_to_string(etc_env)
-            .await
-            .map_err(CogniaError::Io)?
-    } else {
-        String::new()
-    };
+    let content = read_optional_file(&etc_env_path).await?.unwrap_or_default();
+    write_etc_environment(
+        &etc_env_path,
+        upsert_etc_environment_content(&content, key, value),
+    )
+    .await
+}
 
+#[cfg(not(windows))]
+fn upsert_etc_environment_content(content: &str, key: &str, value: &str) -> String {
     let pattern = format!("{}=", key);
     let new_line = if value.contains(' ') || value.contains(':') {
         format!("{}=\"{}\"", key, value)
@@ -1929,22 +2786,20 @@ async fn upsert_etc_environment(key: &str, value: &str) -> CogniaResult<()> {
     if !output.ends_with('\n') {
         output.push('\n');
     }
-    tokio::fs::write(etc_env, output)
-        .await
-        .map_err(|e| CogniaError::PermissionDenied(format!("Cannot write /etc/environment: {}", e)))
+    output
 }
 
 #[cfg(not(windows))]
 async fn remove_etc_environment_var(key: &str) -> CogniaResult<()> {
     let etc_env_path = system_environment_path();
-    let etc_env = etc_env_path.as_path();
-    if !etc_env.exists() {
+    let Some(content) = read_optional_file(&etc_env_path).await? else {
         return Ok(());
-    }
+    };
+    write_etc_environment(&etc_env_path, remove_etc_environment_content(&content, key)).await
+}
 
-    let content = tokio::fs::read_to_string(etc_env)
-        .await
-        .map_err(CogniaError::Io)?;
+#[cfg(not(windows))]
+fn remove_etc_environment_content(content: &str, key: &str) -> String {
     let pattern = format!("{}=", key);
 
     let lines: Vec<&str> = content
@@ -1956,7 +2811,12 @@ async fn remove_etc_environment_var(key: &str) -> CogniaResult<()> {
     if !output.ends_with('\n') && !output.is_empty() {
         output.push('\n');
     }
-    tokio::fs::write(etc_env, output)
+    output
+}
+
+#[cfg(not(windows))]
+async fn write_etc_environment(path: &Path, content: String) -> CogniaResult<()> {
+    tokio::fs::write(path, content)
         .await
         .map_err(|e| CogniaError::PermissionDenied(format!("Cannot write /etc/environment: {}", e)))
 }
@@ -3135,4 +3995,306 @@ fn test_apply_with_system_elevation_fallback_does_not_mask_internal_errors() {
         assert!(matches!(result, Err(CogniaError::Internal(_))));
         assert!(!fallback_called.get());
     }
+
+    #[derive(Default)]
+    struct RecordingEnvStore {
+        values: HashMap<String, StoredEnvValue>,
+        fail_on: Option<String>,
+        log: Vec<String>,
+    }
+
+    impl EnvVarStore for RecordingEnvStore {
+        fn read(&self, key: &str) -> CogniaResult<Option<StoredEnvValue>> {
+            Ok(self.values.get(key).cloned())
+        }
+
+        fn write(&mut self, key: &str, value: &StoredEnvValue) -> CogniaResult<()> {
+            if self.fail_on.as_deref() == Some(key) {
+                return Err(CogniaError::PermissionDenied(format!("{} is locked", key)));
+            }
+            self.log.push(format!("write {}={}", key, value.value));
+            self.values.insert(key.to_string(), value.clone());
+            Ok(())
+        }
+
+        fn delete(&mut self, key: &str) -> CogniaResult<()> {
+            self.log.push(format!("delete {}", key));
+            self.values.remove(key);
+            Ok(())
+        }
+    }
+
+    fn stored(value: &str, expand: bool) -> StoredEnvValue {
+        StoredEnvValue {
+            value: value.to_string(),
+            expand,
+        }
+    }
+
+    #[test]
+    fn test_env_transaction_rolls_back_applied_changes_in_reverse_order() {
+        let mut store = RecordingEnvStore::default();
+        store
+            .values
+            .insert("COGNIA_A".to_string(), stored("old-a", false));
+        store.fail_on = Some("COGNIA_C".to_string());
+
+        let staged = StagedEnvChanges::from_operations(&[
+            EnvVarOperation::Set {
+                key: "COGNIA_A".to_string(),
+                value: "new-a".to_string(),
+            },
+            EnvVarOperation::Set {
+                key: "COGNIA_B".to_string(),
+                value: "new-b".to_string(),
+            },
+            EnvVarOperation::Set {
+                key: "COGNIA_C".to_string(),
+                value: "new-c".to_string(),
+            },
+        ])
+        .unwrap();
+        let (planned, _) = plan_store_changes(&store, &staged, "PATH").unwrap();
+        assert_eq!(planned.len(), 3);
+
+        let err = apply_planned_changes(&mut store, &planned).unwrap_err();
+        assert!(matches!(err, CogniaError::PermissionDenied(_)));
+        assert!(err.to_string().contains("rolled back 2 change(s)"));
+        assert_eq!(
+            store.log,
+            vec![
+                "write COGNIA_A=new-a",
+                "write COGNIA_B=new-b",
+                "delete COGNIA_B",
+                "write COGNIA_A=old-a",
+            ]
+        );
+        assert_eq!(store.values.get("COGNIA_A"), Some(&stored("old-a", false)));
+        assert!(!store.values.contains_key("COGNIA_B"));
+    }
+
+    #[test]
+    fn test_env_transaction_preserves_expand_references_in_path() {
+        let mut store = RecordingEnvStore::default();
+        let current = ["%USERPROFILE%\\bin", "C:\\Tools"].join(PATH_LIST_SEPARATOR);
+        store
+            .values
+            .insert("Path".to_string(), stored(&current, true));
+        store
+            .values
+            .insert("JAVA_HOME".to_string(), stored("C:\\Java", false));
+        store
+            .values
+            .insert("COGNIA_HOME".to_string(), stored("%LOCALAPPDATA%\\x", true));
+
+        let mut transaction = EnvVarTransaction::new(EnvVarScope::User);
+        transaction
+            .path_add("%LOCALAPPDATA%\\cognia\\bin", true)
+            .path_remove("C:\\Tools")
+            .set("JAVA_HOME", "%ProgramFiles%\\Java")
+            .set("COGNIA_HOME", "D:\\cognia");
+        let staged = StagedEnvChanges::from_operations(&transaction.operations).unwrap();
+        let (planned, path_entries) = plan_store_changes(&store, &staged, "Path").unwrap();
+
+        assert_eq!(
+            path_entries,
+            Some(vec![
+                "%LOCALAPPDATA%\\cognia\\bin".to_string(),
+                "%USERPROFILE%\\bin".to_string(),
+            ])
+        );
+        let path = planned.iter().find(|change| change.key == "Path").unwrap();
+        assert_eq!(
+            path.after,
+            Some(stored(
+                &["%LOCALAPPDATA%\\cognia\\bin", "%USERPROFILE%\\bin"].join(PATH_LIST_SEPARATOR),
+                true
+            ))
+        );
+        let java = planned
+            .iter()
+            .find(|change| change.key == "JAVA_HOME")
+            .unwrap();
+        assert_eq!(java.after, Some(stored("%ProgramFiles%\\Java", true)));
+        let home = planned
+            .iter()
+            .find(|change| change.key == "COGNIA_HOME")
+            .unwrap();
+        assert_eq!(home.after, Some(stored("D:\\cognia", true)));
+    }
+
+    #[test]
+    fn test_apply_path_edits_skips_inherited_path_reference() {
+        let entries = apply_path_edits(
+            vec!["/opt/bin".to_string()],
+            &[
+                EnvVarOperation::PathAdd {
+                    entry: "$PATH".to_string(),
+                    prepend: false,
+                },
+                EnvVarOperation::PathAdd {
+                    entry: "${PATH}".to_string(),
+                    prepend: true,
+                },
+                EnvVarOperation::PathAdd {
+                    entry: "/usr/local/go/bin".to_string(),
+                    prepend: false,
+                },
+            ],
+        );
+        assert_eq!(entries, vec!["/opt/bin", "/usr/local/go/bin"]);
+        assert!(is_inherited_path_reference("%Path%"));
+        assert!(!is_inherited_path_reference("/opt/pathtools/bin"));
+    }
+
+    #[test]
+    fn test_env_transaction_staging_folds_and_validates_operations() {
+        let staged = StagedEnvChanges::from_operations(&[
+            EnvVarOperation::Set {
+                key: "COGNIA_X".to_string(),
+                value: "1".to_string(),
+            },
+            EnvVarOperation::Set {
+                key: "COGNIA_Y".to_string(),
+                value: "2".to_string(),
+            },
+            EnvVarOperation::Remove {
+                key: "COGNIA_X".to_string(),
+            },
+        ])
+        .unwrap();
+        assert_eq!(
+            staged.vars,
+            vec![
+                ("COGNIA_X".to_string(), None),
+                ("COGNIA_Y".to_string(), Some("2".to_string())),
+            ]
+        );
+
+        let err = StagedEnvChanges::from_operations(&[EnvVarOperation::Set {
+            key: "Path".to_string(),
+            value: "x".to_string(),
+        }])
+        .unwrap_err();
+        assert!(err.to_string().contains("path_add"));
+
+        let json = r#"[{"op":"path_add","entry":"/opt/bin"},{"op":"remove","key":"FOO"}]"#;
+        let parsed: Vec<EnvVarOperation> = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            parsed[0],
+            EnvVarOperation::PathAdd {
+                entry: "/opt/bin".to_string(),
+                prepend: false
+            }
+        );
+    }
+
+    #[cfg(not(windows))]
+    #[tokio::test]
+    async fn test_env_transaction_user_scope_rewrites_profile_once_with_backup() {
+        let fixture = EnvTestFixture::new(ShellType::Bash, true);
+        let rc_path = fixture.shell_config_path();
+        std::fs::write(&rc_path, "export COGNIA_TX_OLD=\"gone\"\n").unwrap();
+
+        let mut transaction = EnvVarTransaction::new(EnvVarScope::User);
+        transaction
+            .set("COGNIA_TX_A", "alpha")
+            .remove("COGNIA_TX_OLD")
+            .path_add("/opt/cognia/bin", true);
+        let result = transaction.apply().await.unwrap();
+
+        assert_eq!(
+            result.changed_keys,
+            vec!["COGNIA_TX_A", "COGNIA_TX_OLD", "PATH"]
+        );
+        assert_eq!(
+            result.path_entries,
+            Some(vec!["/opt/cognia/bin".to_string()])
+        );
+
+        let content = std::fs::read_to_string(&rc_path).unwrap();
+        assert!(content.contains("export COGNIA_TX_A=\"alpha\""));
+        assert!(!content.contains("COGNIA_TX_OLD"));
+        assert!(content.contains("export PATH=\"/opt/cognia/bin:$PATH\""));
+
+        let backup = std::fs::read_to_string(env_backup_path(&rc_path)).unwrap();
+        assert_eq!(backup, "export COGNIA_TX_OLD=\"gone\"\n");
+    }
+
+    #[cfg(not(windows))]
+    #[tokio::test]
+    async fn test_apply_file_rewrites_restores_earlier_files_on_failure() {
+        let dir = tempdir().unwrap();
+        let first = dir.path().join("first.env");
+        std::fs::write(&first, "A=1\n").unwrap();
+        let created = dir.path().join("created.env");
+        // A file where the third profile's directory should be
+        let not_a_dir = dir.path().join("not-a-dir");
+        std::fs::write(&not_a_dir, "").unwrap();
+
+        let rewrites = vec![
+            FileRewrite {
+                path: first.clone(),
+                original: Some("A=1\n".to_string()),
+                updated: "A=2\n".to_string(),
+            },
+            FileRewrite {
+                path: created.clone(),
+                original: None,
+                updated: "B=1\n".to_string(),
+            },
+            FileRewrite {
+                path: not_a_dir.join("third.env"),
+                original: None,
+                updated: "C=1\n".to_string(),
+            },
+        ];
+
+        let err = apply_file_rewrites(&rewrites).await.unwrap_err();
+        assert!(err.to_string().contains("rolled back 3 change(s)"));
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), "A=1\n");
+        assert!(!created.exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_apply_file_rewrites_restores_file_whose_write_failed() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let first = dir.path().join("first.env");
+        std::fs::write(&first, b"A=1\n# first\n").unwrap();
+        let locked = dir.path().join("locked");
+        std::fs::create_dir(&locked).unwrap();
+        let second = locked.join("second.env");
+        std::fs::write(&second, b"B=1\n# second\n").unwrap();
+        // The backup is writable, but the profile's own write cannot create
+        // its temp file in the read-only directory
+        std::fs::write(env_backup_path(&second), b"").unwrap();
+        std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o555)).unwrap();
+        if std::fs::write(locked.join("probe"), b"").is_ok() {
+            // Permissions are not enforced (running as root)
+            std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o755)).unwrap();
+            return;
+        }
+
+        let rewrites = vec![
+            FileRewrite {
+                path: first.clone(),
+                original: Some("A=1\n# first\n".to_string()),
+                updated: "A=2\n".to_string(),
+            },
+            FileRewrite {
+                path: second.clone(),
+                original: Some("B=1\n# second\n".to_string()),
+                updated: "B=2\n".to_string(),
+            },
+        ];
+        let err = apply_file_rewrites(&rewrites).await;
+        std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        assert!(err.is_err());
+        assert_eq!(std::fs::read(&first).unwrap(), b"A=1\n# first\n");
+        assert_eq!(std::fs::read(&second).unwrap(), b"B=1\n# second\n");
+    }
 }
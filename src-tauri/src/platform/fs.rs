@@ -5,7 +5,7 @@
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 use tokio::fs;
-use tokio::io::AsyncReadExt;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 #[derive(Error, Debug)]
 pub enum FsError {
@@ -67,8 +67,19 @@ pub async fn write_file_atomic(path: impl AsRef<Path>, contents: &[u8]) -> FsRes
     let temp_path = parent.join(format!(".{}.tmp", uuid::Uuid::new_v4()));
 
     let result = async {
-        fs::write(&temp_path, contents).await?;
+        let mut file = fs::File::create(&temp_path).await?;
+        // Keep the mode of the file being replaced, e.g. a 0600 config
+        // holding credentials, before any content is written
+        if let Ok(meta) = fs::metadata(path).await {
+            file.set_permissions(meta.permissions()).await?;
+        }
+        file.write_all(contents).await?;
+        // Flush to disk before the rename so a crash never leaves the
+        // target pointing at a partially written file
+        file.sync_all().await?;
+        drop(file);
         fs::rename(&temp_path, path).await?;
+        sync_dir(parent).await;
         Ok::<_, io::Error>(())
     }
     .await;
@@ -80,6 +91,111 @@ pub async fn write_file_atomic(path: impl AsRef<Path>, contents: &[u8]) -> FsRes
     result.map_err(FsError::Io)
 }
 
+/// Persist a rename by syncing its directory. Best effort, and a no-op
+/// where directories cannot be opened as files.
+async fn sync_dir(dir: &Path) {
+    #[cfg(unix)]
+    {
+        if let Ok(handle) = fs::File::open(dir).await {
+            let _ = handle.sync_all().await;
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = dir;
+}
+
+/// Where [`atomic_write_json`] keeps the previous version of `path`,
+/// e.g. `profiles.json.bak`
+pub fn backup_path(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!("{}.bak", file_name))
+}
+
+/// Serialize `value` as pretty JSON and write it with
+/// [`write_file_atomic`]. With `keep_backup`, the current file is first
+/// copied to [`backup_path`], unless it no longer parses, so a good
+/// backup is never replaced by a torn file.
+pub async fn atomic_write_json<T: serde::Serialize + ?Sized>(
+    path: impl AsRef<Path>,
+    value: &T,
+    keep_backup: bool,
+) -> FsResult<()> {
+    let path = path.as_ref();
+    let content = serde_json::to_vec_pretty(value).map_err(io::Error::from)?;
+
+    if keep_backup {
+        if let Ok(current) = fs::read(path).await {
+            if serde_json::from_slice::<serde::de::IgnoredAny>(&current).is_ok() {
+                write_file_atomic(backup_path(path), &current).await?;
+            }
+        }
+    }
+
+    write_file_atomic(path, &content).await
+}
+
+/// Result of [`read_json_with_backup`]
+#[derive(Debug)]
+pub enum JsonLoad<T> {
+    /// Neither the file nor its backup exists
+    Missing,
+    Loaded(T),
+    /// The file was unreadable and the backup was used instead
+    Recovered {
+        value: T,
+        error: String,
+    },
+}
+
+impl<T> JsonLoad<T> {
+    /// The loaded value, logging a warning if it came from the backup
+    pub fn into_value(self, what: &str) -> Option<T> {
+        match self {
+            Self::Missing => None,
+            Self::Loaded(value) => Some(value),
+            Self::Recovered { value, error } => {
+                log::warn!("{} was unreadable ({}), restored from backup", what, error);
+                Some(value)
+            }
+        }
+    }
+}
+
+/// Read JSON written by [`atomic_write_json`], falling back to the backup
+/// when the file is missing, unreadable or does not parse. Fails only when
+/// neither copy is usable.
+pub async fn read_json_with_backup<T: serde::de::DeserializeOwned>(
+    path: impl AsRef<Path>,
+) -> FsResult<JsonLoad<T>> {
+    let path = path.as_ref();
+    let backup = backup_path(path);
+
+    let primary_error = match fs::read(path).await {
+        Ok(content) => match serde_json::from_slice(&content) {
+            Ok(value) => return Ok(JsonLoad::Loaded(value)),
+            Err(e) => io::Error::new(io::ErrorKind::InvalidData, e),
+        },
+        Err(e) => e,
+    };
+    if primary_error.kind() == io::ErrorKind::NotFound && !exists(&backup).await {
+        return Ok(JsonLoad::Missing);
+    }
+
+    match fs::read(&backup).await {
+        Ok(content) => match serde_json::from_slice(&content) {
+            Ok(value) => Ok(JsonLoad::Recovered {
+                value,
+                error: primary_error.to_string(),
+            }),
+            Err(_) => Err(FsError::Io(primary_error)),
+        },
+        Err(_) => Err(FsError::Io(primary_error)),
+    }
+}
+
 pub async fn copy_file(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> FsResult<u64> {
     let src = src.as_ref();
     let dst = dst.as_ref();
@@ -449,6 +565,89 @@ async fn test_atomic_write() {
         assert_eq!(content, b"atomic content");
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_atomic_write_keeps_mode() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(".npmrc");
+        std::fs::write(&path, "//registry/:_authToken=x\n").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+
+        write_file_atomic(&path, b"registry=https://mirror/\n")
+            .await
+            .unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[tokio::test]
+    async fn test_atomic_write_json_keeps_backup() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("state.json");
+
+        atomic_write_json(&path, &[1], true).await.unwrap();
+        assert!(!exists(backup_path(&path)).await);
+        atomic_write_json(&path, &[1, 2], true).await.unwrap();
+
+        let backup: Vec<i32> =
+            serde_json::from_str(&read_file_string(backup_path(&path)).await.unwrap()).unwrap();
+        assert_eq!(backup, vec![1]);
+        match read_json_with_backup::<Vec<i32>>(&path).await.unwrap() {
+            JsonLoad::Loaded(value) => assert_eq!(value, vec![1, 2]),
+            other => panic!("unexpected load result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_json_recovers_torn_write() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("state.json");
+        atomic_write_json(&path, &["a", "b"], true).await.unwrap();
+        atomic_write_json(&path, &["a", "b", "c"], true)
+            .await
+            .unwrap();
+
+        // Simulate a crash halfway through a plain write
+        let full = read_file(&path).await.unwrap();
+        write_file(&path, &full[..full.len() / 2]).await.unwrap();
+
+        match read_json_with_backup::<Vec<String>>(&path).await.unwrap() {
+            JsonLoad::Recovered { value, error } => {
+                assert_eq!(value, vec!["a", "b"]);
+                assert!(!error.is_empty());
+            }
+            other => panic!("unexpected load result: {:?}", other),
+        }
+
+        // The torn file must not overwrite the good backup
+        atomic_write_json(&path, &["d"], true).await.unwrap();
+        let backup: Vec<String> =
+            serde_json::from_str(&read_file_string(backup_path(&path)).await.unwrap()).unwrap();
+        assert_eq!(backup, vec!["a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn test_read_json_missing_and_unrecoverable() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("state.json");
+        assert!(matches!(
+            read_json_with_backup::<Vec<i32>>(&path).await.unwrap(),
+            JsonLoad::Missing
+        ));
+
+        write_file_string(&path, "[1, 2").await.unwrap();
+        assert!(read_json_with_backup::<Vec<i32>>(&path).await.is_err());
+
+        // A lost primary file falls back to the backup as well
+        write_file_string(backup_path(&path), "[3]").await.unwrap();
+        remove_file(&path).await.unwrap();
+        match read_json_with_backup::<Vec<i32>>(&path).await.unwrap() {
+            JsonLoad::Recovered { value, .. } => assert_eq!(value, vec![3]),
+            other => panic!("unexpected load result: {:?}", other),
+        }
+    }
+
     #[tokio::test]
     async fn test_copy_file() {
         let dir = tempdir().unwrap();
@@ -0,0 +1,529 @@
+//! Record/replay of provider HTTP traffic.
+//!
+//! Requests sent through [`send`] normally go straight to the network. With
+//! `COGNIA_HTTP_REPLAY=record`, each response is also written to a fixture
+//! file keyed by the normalized request; with `COGNIA_HTTP_REPLAY=replay`,
+//! requests are answered from those files and never leave the process.
+//! `COGNIA_HTTP_FIXTURES` names the fixture directory. Tests scope a
+//! [`ReplaySession`] to a future instead of going through the environment.
+//!
+//! Only API calls are covered; file downloads stream from the network as
+//! usual.
+
+use once_cell::sync::Lazy;
+use reqwest::{Request, RequestBuilder, Response, Url};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+pub const REPLAY_MODE_ENV: &str = "COGNIA_HTTP_REPLAY";
+pub const REPLAY_FIXTURES_ENV: &str = "COGNIA_HTTP_FIXTURES";
+
+/// Response headers kept in fixtures. Everything else is either noise (dates,
+/// CDN ids) or could carry credentials.
+const RECORDED_HEADERS: &[&str] = &[
+    "content-type",
+    "etag",
+    "last-modified",
+    "link",
+    "retry-after",
+    "x-ratelimit-limit",
+    "x-ratelimit-remaining",
+    "x-ratelimit-reset",
+    "x-ratelimit-resource",
+    "x-ratelimit-used",
+];
+
+/// Query parameters left out of fixture keys so recordings never hold secrets
+const REDACTED_QUERY_PARAMS: &[&str] =
+    &["access_token", "api_key", "client_secret", "key", "token"];
+
+const MAX_FIXTURE_STEM: usize = 96;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayMode {
+    /// Hit the network and save every response as a fixture
+    Record,
+    /// Serve fixtures only; requests without one fail
+    Replay,
+}
+
+impl std::str::FromStr for ReplayMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "record" => Ok(Self::Record),
+            "replay" => Ok(Self::Replay),
+            other => Err(format!(
+                "Invalid {} value `{}`. Valid: record, replay",
+                REPLAY_MODE_ENV, other
+            )),
+        }
+    }
+}
+
+/// Where fixtures live and which requests had none
+#[derive(Debug, Clone)]
+pub struct ReplaySession {
+    mode: ReplayMode,
+    fixtures_dir: PathBuf,
+    misses: Arc<Mutex<Vec<String>>>,
+}
+
+tokio::task_local! {
+    static SCOPED_SESSION: ReplaySession;
+}
+
+static ENV_SESSION: Lazy<Option<ReplaySession>> = Lazy::new(ReplaySession::from_env);
+
+impl ReplaySession {
+    pub fn new(mode: ReplayMode, fixtures_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            mode,
+            fixtures_dir: fixtures_dir.into(),
+            misses: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    fn from_env() -> Option<Self> {
+        let mode = std::env::var(REPLAY_MODE_ENV).ok()?;
+        let mode = match mode.parse::<ReplayMode>() {
+            Ok(mode) => mode,
+            Err(e) => {
+                log::warn!("{}", e);
+                return None;
+            }
+        };
+        let fixtures_dir = std::env::var(REPLAY_FIXTURES_ENV)
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| default_fixtures_dir());
+        log::info!(
+            "HTTP {:?} mode, fixtures in {}",
+            mode,
+            fixtures_dir.display()
+        );
+        Some(Self::new(mode, fixtures_dir))
+    }
+
+    pub fn mode(&self) -> ReplayMode {
+        self.mode
+    }
+
+    pub fn fixtures_dir(&self) -> &Path {
+        &self.fixtures_dir
+    }
+
+    /// Requests that had no fixture while replaying, as `METHOD url`
+    pub fn misses(&self) -> Vec<String> {
+        self.misses
+            .lock()
+            .map(|m| m.clone())
+            .unwrap_or_else(|e| e.into_inner().clone())
+    }
+
+    /// Runs `future` with this session active for every request it sends
+    pub async fn scope<F: Future>(&self, future: F) -> F::Output {
+        SCOPED_SESSION.scope(self.clone(), future).await
+    }
+
+    fn record_miss(&self, key: &RequestKey) {
+        let miss = format!("{} {}", key.method, key.url);
+        log::error!(
+            "No replay fixture for {} (expected {})",
+            miss,
+            self.fixtures_dir.join(key.file_name()).display()
+        );
+        match self.misses.lock() {
+            Ok(mut misses) => misses.push(miss),
+            Err(e) => e.into_inner().push(miss),
+        }
+    }
+}
+
+/// Fixtures checked into the repository
+pub fn default_fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("http")
+}
+
+fn active_session() -> Option<ReplaySession> {
+    SCOPED_SESSION
+        .try_with(ReplaySession::clone)
+        .ok()
+        .or_else(|| ENV_SESSION.clone())
+}
+
+/// Request identity used to name fixtures: method, URL with sorted query
+/// parameters, and a digest of the body if there is one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestKey {
+    pub method: String,
+    pub url: String,
+    pub body_digest: Option<String>,
+}
+
+impl RequestKey {
+    pub fn new(method: &str, url: &Url, body: Option<&[u8]>) -> Self {
+        Self {
+            method: method.to_uppercase(),
+            url: normalize_url(url),
+            body_digest: body.filter(|b| !b.is_empty()).map(digest),
+        }
+    }
+
+    fn of(request: &Request) -> Self {
+        let body = request.body().and_then(|b| b.as_bytes());
+        Self::new(request.method().as_str(), request.url(), body)
+    }
+
+    /// Fixture path relative to the fixture directory:
+    /// `<host>/<method>_<path>__<query>.json`, with the method prefix left out
+    /// for GET and a digest appended when the name would be ambiguous
+    pub fn file_name(&self) -> PathBuf {
+        let url = Url::parse(&self.url).ok();
+        let host = url
+            .as_ref()
+            .and_then(|u| {
+                u.host_str().map(|h| match u.port() {
+                    Some(port) => format!("{}_{}", h, port),
+                    None => h.to_string(),
+                })
+            })
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let mut stem = String::new();
+        if self.method != "GET" {
+            stem.push_str(&self.method.to_lowercase());
+            stem.push('_');
+        }
+        if let Some(url) = &url {
+            let path = url.path().trim_matches('/');
+            stem.push_str(if path.is_empty() { "index" } else { path });
+            if let Some(query) = url.query() {
+                stem.push_str("__");
+                stem.push_str(query);
+            }
+        }
+        let mut stem: String = stem
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '=') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        if stem.len() > MAX_FIXTURE_STEM || self.body_digest.is_some() {
+            let full = format!(
+                "{} {} {}",
+                self.method,
+                self.url,
+                self.body_digest.as_deref().unwrap_or("")
+            );
+            stem.truncate(MAX_FIXTURE_STEM - 13);
+            stem.push('-');
+            stem.push_str(&digest(full.as_bytes())[..12]);
+        }
+
+        Path::new(&host).join(format!("{}.json", stem))
+    }
+}
+
+fn digest(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+fn normalize_url(url: &Url) -> String {
+    let mut url = url.clone();
+    url.set_fragment(None);
+    let mut pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(k, _)| !REDACTED_QUERY_PARAMS.contains(&k.to_lowercase().as_str()))
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect();
+    pairs.sort();
+    if pairs.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+    url.to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixtureRequest {
+    pub method: String,
+    pub url: String,
+}
+
+/// One recorded response. JSON bodies are stored as JSON so fixtures stay
+/// readable and editable; anything else is kept as text.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpFixture {
+    pub request: FixtureRequest,
+    pub status: u16,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+impl HttpFixture {
+    async fn capture(key: &RequestKey, response: Response) -> reqwest::Result<Self> {
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .filter(|(name, _)| RECORDED_HEADERS.contains(&name.as_str()))
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let bytes = response.bytes().await?;
+        let (json, text) = match serde_json::from_slice(&bytes) {
+            Ok(json) => (Some(json), None),
+            Err(_) => (None, Some(String::from_utf8_lossy(&bytes).into_owned())),
+        };
+        Ok(Self {
+            request: FixtureRequest {
+                method: key.method.clone(),
+                url: key.url.clone(),
+            },
+            status,
+            headers,
+            json,
+            text,
+        })
+    }
+
+    fn body(&self) -> Vec<u8> {
+        match (&self.json, &self.text) {
+            (Some(json), _) => serde_json::to_vec(json).unwrap_or_default(),
+            (None, Some(text)) => text.clone().into_bytes(),
+            (None, None) => Vec::new(),
+        }
+    }
+
+    pub fn into_response(self) -> Response {
+        let mut builder = tauri::http::Response::builder().status(self.status);
+        for (name, value) in &self.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        if self.json.is_some() && !self.headers.contains_key("content-type") {
+            builder = builder.header("content-type", "application/json");
+        }
+        let body = self.body();
+        match builder.body(body) {
+            Ok(response) => Response::from(response),
+            Err(e) => error_response(500, format!("Invalid replay fixture: {}", e)),
+        }
+    }
+}
+
+fn error_response(status: u16, message: String) -> Response {
+    let response = tauri::http::Response::builder()
+        .status(status)
+        .header("content-type", "text/plain")
+        .body(message.clone().into_bytes())
+        .unwrap_or_else(|_| tauri::http::Response::new(message.into_bytes()));
+    Response::from(response)
+}
+
+async fn read_fixture(path: &Path) -> Option<HttpFixture> {
+    let content = tokio::fs::read_to_string(path).await.ok()?;
+    match serde_json::from_str(&content) {
+        Ok(fixture) => Some(fixture),
+        Err(e) => {
+            log::error!("Invalid replay fixture {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+async fn write_fixture(path: &Path, fixture: &HttpFixture) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let mut content = serde_json::to_string_pretty(fixture).map_err(std::io::Error::other)?;
+    content.push('\n');
+    tokio::fs::write(path, content).await
+}
+
+/// Sends `request`, recording or replaying it when a session is active.
+/// While replaying, a request without a fixture gets a 404 and is noted in
+/// [`ReplaySession::misses`].
+pub async fn send(request: RequestBuilder) -> reqwest::Result<Response> {
+    let Some(session) = active_session() else {
+        return request.send().await;
+    };
+
+    let (client, request) = request.build_split();
+    let request = request?;
+    let key = RequestKey::of(&request);
+    let path = session.fixtures_dir.join(key.file_name());
+
+    match session.mode {
+        ReplayMode::Replay => Ok(match read_fixture(&path).await {
+            Some(fixture) => fixture.into_response(),
+            None => {
+                session.record_miss(&key);
+                error_response(
+                    404,
+                    format!("No replay fixture for {} {}", key.method, key.url),
+                )
+            }
+        }),
+        ReplayMode::Record => {
+            let response = client.execute(request).await?;
+            let fixture = HttpFixture::capture(&key, response).await?;
+            if let Err(e) = write_fixture(&path, &fixture).await {
+                log::warn!("Failed to record {}: {}", path.display(), e);
+            }
+            Ok(fixture.into_response())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn key(method: &str, url: &str) -> RequestKey {
+        RequestKey::new(method, &Url::parse(url).unwrap(), None)
+    }
+
+    #[test]
+    fn test_replay_mode_parse() {
+        assert_eq!("Record".parse::<ReplayMode>(), Ok(ReplayMode::Record));
+        assert_eq!(" replay ".parse::<ReplayMode>(), Ok(ReplayMode::Replay));
+        assert!("live".parse::<ReplayMode>().is_err());
+    }
+
+    #[test]
+    fn test_request_key_sorts_query_and_drops_secrets() {
+        let a = key(
+            "get",
+            "https://API.github.com/repos/cli/cli/releases?per_page=20&access_token=abc#top",
+        );
+        let b = key(
+            "GET",
+            "https://api.github.com/repos/cli/cli/releases?per_page=20",
+        );
+        assert_eq!(a, b);
+        assert_eq!(
+            a.url,
+            "https://api.github.com/repos/cli/cli/releases?per_page=20"
+        );
+
+        let c = key(
+            "GET",
+            "https://registry.npmjs.org/-/v1/search?text=react&size=2",
+        );
+        assert_eq!(
+            c.url,
+            "https://registry.npmjs.org/-/v1/search?size=2&text=react"
+        );
+    }
+
+    #[test]
+    fn test_fixture_file_names() {
+        assert_eq!(
+            key("GET", "https://pypi.org/pypi/requests/json").file_name(),
+            Path::new("pypi.org").join("pypi_requests_json.json")
+        );
+        assert_eq!(
+            key(
+                "GET",
+                "https://registry.npmjs.org/-/v1/search?text=react&size=2"
+            )
+            .file_name(),
+            Path::new("registry.npmjs.org").join("-_v1_search__size=2_text=react.json")
+        );
+        assert_eq!(
+            key("GET", "http://localhost:8080/").file_name(),
+            Path::new("localhost_8080").join("index.json")
+        );
+
+        let post = RequestKey::new(
+            "POST",
+            &Url::parse("https://example.com/graphql").unwrap(),
+            Some(b"{\"query\":1}"),
+        );
+        let name = post.file_name().to_string_lossy().into_owned();
+        assert!(name.contains("post_graphql-"));
+
+        let long = key("GET", &format!("https://example.com/{}", "a".repeat(300)));
+        let stem = long.file_name();
+        let stem = stem.file_stem().unwrap().to_string_lossy();
+        assert!(stem.len() <= MAX_FIXTURE_STEM);
+    }
+
+    #[tokio::test]
+    async fn test_fixture_round_trips_through_response() {
+        let fixture = HttpFixture {
+            request: FixtureRequest {
+                method: "GET".into(),
+                url: "https://example.com/api".into(),
+            },
+            status: 200,
+            headers: BTreeMap::from([("etag".to_string(), "\"abc\"".to_string())]),
+            json: Some(serde_json::json!({ "name": "demo", "versions": ["1.0.0"] })),
+            text: None,
+        };
+        let response = fixture.into_response();
+        assert_eq!(response.status().as_u16(), 200);
+        assert_eq!(response.headers()["etag"], "\"abc\"");
+        assert_eq!(response.headers()["content-type"], "application/json");
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["versions"][0], "1.0.0");
+    }
+
+    #[tokio::test]
+    async fn test_replay_serves_fixtures_and_reports_misses() {
+        let dir = TempDir::new().unwrap();
+        let session = ReplaySession::new(ReplayMode::Replay, dir.path());
+        let fixture_key = key("GET", "https://example.invalid/tools?b=2&a=1");
+        let fixture = HttpFixture {
+            request: FixtureRequest {
+                method: fixture_key.method.clone(),
+                url: fixture_key.url.clone(),
+            },
+            status: 200,
+            headers: BTreeMap::new(),
+            json: None,
+            text: Some("hello".into()),
+        };
+        write_fixture(&dir.path().join(fixture_key.file_name()), &fixture)
+            .await
+            .unwrap();
+
+        let client = reqwest::Client::new();
+        let (hit, miss) = session
+            .scope(async {
+                let hit = send(client.get("https://example.invalid/tools?a=1&b=2"))
+                    .await
+                    .unwrap();
+                let miss = send(client.get("https://example.invalid/other"))
+                    .await
+                    .unwrap();
+                (hit, miss)
+            })
+            .await;
+
+        assert_eq!(hit.text().await.unwrap(), "hello");
+        assert_eq!(miss.status().as_u16(), 404);
+        assert_eq!(
+            session.misses(),
+            vec!["GET https://example.invalid/other".to_string()]
+        );
+    }
+}
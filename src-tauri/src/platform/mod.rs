@@ -1,10 +1,14 @@
 pub mod disk;
+pub mod dotenv;
 pub mod env;
 pub mod fs;
+pub mod http_replay;
 pub mod network;
 pub mod paths;
 pub mod process;
 pub mod proxy;
+pub mod scheduler;
+pub mod secrets;
 
 pub use disk::*;
 pub use env::*;
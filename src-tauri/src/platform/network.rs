@@ -102,7 +102,11 @@ pub fn with_retries(mut self, max_retries: u32) -> Self {
 
 #[derive(Clone)]
 pub struct HttpClient {
-    client: Client,
+    /// Fixed client set via `with_proxy`; otherwise resolved per request so
+    /// settings changes apply without rebuilding the owner.
+    client: Option<Client>,
+    /// Provider id used to pick up per-provider proxy overrides.
+    provider: Option<String>,
     default_options: RequestOptions,
 }
 
@@ -115,11 +119,20 @@ fn default() -> Self {
 impl HttpClient {
     pub fn new() -> Self {
         Self {
-            client: super::proxy::get_shared_client(),
+            client: None,
+            provider: None,
             default_options: RequestOptions::new(),
         }
     }
 
+    /// Create a client that routes through the effective proxy for `provider`.
+    pub fn for_provider(provider: &str) -> Self {
+        Self {
+            provider: Some(provider.to_string()),
+            ..Self::new()
+        }
+    }
+
     pub fn with_options(mut self, options: RequestOptions) -> Self {
         self.default_options = options;
         self
@@ -134,14 +147,25 @@ pub fn with_proxy(self, proxy_url: Option<&str>) -> Self {
                         .timeout(Duration::from_secs(30))
                         .user_agent("CogniaLauncher/0.1.0")
                         .build()
-                        .unwrap_or(self.client.clone());
-                    return Self { client, ..self };
+                        .unwrap_or_else(|_| self.client());
+                    return Self {
+                        client: Some(client),
+                        ..self
+                    };
                 }
             }
         }
         self
     }
 
+    fn client(&self) -> Client {
+        match (&self.client, &self.provider) {
+            (Some(client), _) => client.clone(),
+            (None, Some(provider)) => super::proxy::get_client_for_provider(provider),
+            (None, None) => super::proxy::get_shared_client(),
+        }
+    }
+
     pub async fn get(&self, url: &str) -> NetworkResult<Response> {
         self.get_with_options(url, None).await
     }
@@ -155,7 +179,7 @@ pub async fn get_with_options(
         let mut attempts = 0;
 
         loop {
-            let mut request = self.client.get(url);
+            let mut request = self.client().get(url);
 
             if let Some(timeout) = options.timeout {
                 request = request.timeout(timeout);
@@ -165,9 +189,12 @@ pub async fn get_with_options(
                 request = request.header(key.as_str(), value.as_str());
             }
 
-            match request.send().await {
+            match super::http_replay::send(request).await {
                 Ok(response) => {
-                    if response.status().is_success() {
+                    // 304 only comes back to callers that sent a conditional header
+                    if response.status().is_success()
+                        || response.status() == StatusCode::NOT_MODIFIED
+                    {
                         return Ok(response);
                     }
 
@@ -250,7 +277,7 @@ pub async fn post_json<T: Serialize, R: for<'de> Deserialize<'de>>(
         url: &str,
         body: &T,
     ) -> NetworkResult<R> {
-        let response = self.client.post(url).json(body).send().await?;
+        let response = super::http_replay::send(self.client().post(url).json(body)).await?;
 
         if !response.status().is_success() {
             return Err(NetworkError::HttpStatus(
@@ -294,7 +321,7 @@ pub async fn download_with_resume<P, F>(
             tokio::fs::create_dir_all(parent).await?;
         }
 
-        let mut request = self.client.get(url);
+        let mut request = self.client().get(url);
 
         // Apply default headers (e.g. auth tokens for private repos)
         for (key, value) in &self.default_options.headers {
@@ -328,6 +355,8 @@ pub async fn download_with_resume<P, F>(
         let start_time = std::time::Instant::now();
 
         let mut stream = response.bytes_stream();
+        // Installer downloads share the download manager's bandwidth limit
+        let speed_limiter = crate::download::SpeedLimiter::shared();
 
         while let Some(chunk) = stream.next().await {
             let chunk = match chunk {
@@ -336,6 +365,10 @@ pub async fn download_with_resume<P, F>(
                     return Err(classify_request_error(error, self.default_options.timeout))
                 }
             };
+            let mut remaining = chunk.len() as u64;
+            while speed_limiter.is_enabled() && remaining > 0 {
+                remaining -= speed_limiter.acquire(remaining).await;
+            }
             file.write_all(&chunk).await?;
             downloaded += chunk.len() as u64;
 
@@ -361,7 +394,7 @@ pub async fn download_with_resume<P, F>(
     }
 
     pub async fn head(&self, url: &str) -> NetworkResult<Response> {
-        let response = self.client.head(url).send().await?;
+        let response = self.client().head(url).send().await?;
 
         if !response.status().is_success() {
             return Err(NetworkError::HttpStatus(
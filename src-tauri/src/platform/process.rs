@@ -1,8 +1,9 @@
+use serde::Serialize;
 use std::collections::HashMap;
 use std::process::Stdio;
 use std::time::Duration;
 use thiserror::Error;
-use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::process::Command;
 
 #[derive(Error, Debug)]
@@ -33,6 +34,8 @@ pub struct ProcessOptions {
     pub env: HashMap<String, String>,
     pub timeout: Option<Duration>,
     pub capture_output: bool,
+    /// Written to the child's stdin, which is then closed
+    pub stdin: Option<String>,
 }
 
 impl Default for ProcessOptions {
@@ -42,6 +45,7 @@ fn default() -> Self {
             env: HashMap::new(),
             timeout: None,
             capture_output: true,
+            stdin: None,
         }
     }
 }
@@ -70,6 +74,11 @@ pub fn with_capture(mut self, capture: bool) -> Self {
         self.capture_output = capture;
         self
     }
+
+    pub fn with_stdin(mut self, input: impl Into<String>) -> Self {
+        self.stdin = Some(input.into());
+        self
+    }
 }
 
 pub async fn execute(
@@ -77,6 +86,19 @@ pub async fn execute(
     args: &[&str],
     options: Option<ProcessOptions>,
 ) -> ProcessResult<ProcessOutput> {
+    execute_tracked(program, args, options, |_| {}).await
+}
+
+/// Like `execute`, but reports the child's pid to `on_spawn` as soon as it starts.
+pub async fn execute_tracked<S>(
+    program: &str,
+    args: &[&str],
+    options: Option<ProcessOptions>,
+    on_spawn: S,
+) -> ProcessResult<ProcessOutput>
+where
+    S: FnOnce(Option<u32>),
+{
     let options = options.unwrap_or_default();
 
     let mut cmd = Command::new(program);
@@ -95,6 +117,10 @@ pub async fn execute(
         cmd.stderr(Stdio::piped());
     }
 
+    if options.stdin.is_some() {
+        cmd.stdin(Stdio::piped());
+    }
+
     // Prevent console window from flashing on Windows when spawning console apps from GUI
     #[cfg(windows)]
     {
@@ -103,6 +129,12 @@ pub async fn execute(
     }
 
     let mut child = cmd.spawn()?;
+    on_spawn(child.id());
+
+    if let (Some(input), Some(mut stdin)) = (options.stdin.as_ref(), child.stdin.take()) {
+        stdin.write_all(input.as_bytes()).await?;
+        drop(stdin);
+    }
 
     let stdout_task = child.stdout.take().map(|mut stdout| {
         tokio::spawn(async move {
@@ -182,10 +214,28 @@ pub async fn execute_with_streaming<F, G>(
     program: &str,
     args: &[&str],
     options: Option<ProcessOptions>,
+    on_stdout: F,
+    on_stderr: G,
+) -> ProcessResult<ProcessOutput>
+where
+    F: FnMut(&str),
+    G: FnMut(&str),
+{
+    execute_with_streaming_tracked(program, args, options, |_| {}, on_stdout, on_stderr).await
+}
+
+/// Like `execute_with_streaming`, but reports the child's pid to `on_spawn`
+/// as soon as it starts.
+pub async fn execute_with_streaming_tracked<S, F, G>(
+    program: &str,
+    args: &[&str],
+    options: Option<ProcessOptions>,
+    on_spawn: S,
     mut on_stdout: F,
     mut on_stderr: G,
 ) -> ProcessResult<ProcessOutput>
 where
+    S: FnOnce(Option<u32>),
     F: FnMut(&str),
     G: FnMut(&str),
 {
@@ -212,6 +262,7 @@ pub async fn execute_with_streaming<F, G>(
     }
 
     let mut child = cmd.spawn()?;
+    on_spawn(child.id());
 
     let stdout = child.stdout.take().expect("stdout not captured");
     let stderr = child.stderr.take().expect("stderr not captured");
@@ -367,6 +418,284 @@ pub async fn which(program: &str) -> Option<String> {
     })
 }
 
+/// Every match for `program` across the directories of `path_var`, in PATH
+/// order, one per directory. Unlike [`which`], shadowed copies are returned
+/// too; [`which_detailed_in`] also says what each match is.
+pub fn which_all_in(program: &str, path_var: &std::ffi::OsStr) -> Vec<std::path::PathBuf> {
+    let mut matches: Vec<std::path::PathBuf> = Vec::new();
+    for found in path_matches(program, path_var) {
+        if !found.runnable || matches.last().and_then(|m| m.parent()) == found.path.parent() {
+            continue;
+        }
+        matches.push(found.path);
+    }
+    matches
+}
+
+/// [`which_all_in`] over the current process PATH.
+pub fn which_all(program: &str) -> Vec<std::path::PathBuf> {
+    match std::env::var_os("PATH") {
+        Some(path_var) => which_all_in(program, &path_var),
+        None => Vec::new(),
+    }
+}
+
+/// How a PATH match runs when started
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProgramFileType {
+    NativeExecutable,
+    /// `.cmd` / `.bat`, run through `cmd.exe`
+    CmdWrapper,
+    /// `.ps1`, which process creation never picks for a bare name
+    PowerShellScript,
+    /// Started through its `#!` interpreter or a Windows script host
+    Script,
+    /// Windows binary reached through WSL interop, or a Linux binary on a
+    /// `\\wsl$` share
+    WslInteropStub,
+    /// Shim generated by Cognia, see `core::shim`
+    CogniaShim,
+    /// Present on PATH but not runnable as-is on this platform
+    NotExecutable,
+}
+
+/// One match for a program name on PATH
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgramCandidate {
+    pub path: String,
+    pub file_type: ProgramFileType,
+    /// Final target when the match is a symlink
+    pub symlink_target: Option<String>,
+    /// Version resource of a Windows executable
+    pub file_version: Option<String>,
+    /// Whether `CreateProcess` (with PATHEXT) or `execvp` picks this match
+    pub selected: bool,
+}
+
+/// Default Windows PATHEXT, used when the variable is unset
+#[cfg(windows)]
+const DEFAULT_PATHEXT: &str = ".COM;.EXE;.BAT;.CMD;.VBS;.VBE;.JS;.JSE;.WSF;.WSH;.MSC";
+
+#[cfg(windows)]
+fn pathext_from_env() -> Vec<String> {
+    let value = std::env::var("PATHEXT").unwrap_or_else(|_| DEFAULT_PATHEXT.to_string());
+    value
+        .split(';')
+        .map(|ext| ext.trim().to_ascii_lowercase())
+        .filter(|ext| ext.starts_with('.') && ext.len() > 1)
+        .collect()
+}
+
+/// File names to probe in each PATH directory, in the order cmd.exe tries
+/// them, and whether a match is runnable by bare name. A name that already
+/// carries a PATHEXT extension is only tried as-is. Extension-less files and
+/// PowerShell scripts are reported but never selected.
+#[cfg(windows)]
+fn windows_candidate_names(program: &str, pathext: &[String]) -> Vec<(String, bool)> {
+    let lower = program.to_ascii_lowercase();
+    if pathext.iter().any(|ext| lower.ends_with(ext.as_str())) {
+        return vec![(program.to_string(), true)];
+    }
+    let mut names: Vec<(String, bool)> = pathext
+        .iter()
+        .map(|ext| (format!("{}{}", program, ext), true))
+        .collect();
+    names.push((program.to_string(), false));
+    if !lower.ends_with(".ps1") {
+        names.push((format!("{}.ps1", program), false));
+    }
+    names
+}
+
+#[cfg(windows)]
+fn candidate_names(program: &str) -> Vec<(String, bool)> {
+    windows_candidate_names(program, &pathext_from_env())
+}
+
+/// Under WSL, `<program>.exe` from the Windows PATH is listed too: it only
+/// runs when named explicitly, so `execvp` never picks it for a bare name
+#[cfg(not(windows))]
+fn candidate_names(program: &str) -> Vec<(String, bool)> {
+    let mut names = vec![(program.to_string(), true)];
+    let in_wsl = std::env::var_os("WSL_DISTRO_NAME").is_some()
+        || std::path::Path::new("/proc/sys/fs/binfmt_misc/WSLInterop").exists();
+    if in_wsl && !program.ends_with(".exe") {
+        names.push((format!("{}.exe", program), false));
+    }
+    names
+}
+
+fn read_magic(path: &std::path::Path) -> [u8; 4] {
+    use std::io::Read;
+    let mut magic = [0u8; 4];
+    if let Ok(mut file) = std::fs::File::open(path) {
+        let _ = file.read(&mut magic);
+    }
+    magic
+}
+
+#[cfg(windows)]
+fn classify_program_file(path: &std::path::Path, _metadata: &std::fs::Metadata) -> ProgramFileType {
+    let display = path.to_string_lossy().to_ascii_lowercase();
+    if display.starts_with(r"\\wsl$\") || display.starts_with(r"\\wsl.localhost\") {
+        return ProgramFileType::WslInteropStub;
+    }
+    let ext = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "exe" | "com" => ProgramFileType::NativeExecutable,
+        "cmd" | "bat" => ProgramFileType::CmdWrapper,
+        "ps1" => ProgramFileType::PowerShellScript,
+        "" if read_magic(path).starts_with(b"#!") => ProgramFileType::Script,
+        "" => ProgramFileType::NotExecutable,
+        _ => ProgramFileType::Script,
+    }
+}
+
+#[cfg(unix)]
+fn classify_program_file(path: &std::path::Path, metadata: &std::fs::Metadata) -> ProgramFileType {
+    use std::os::unix::fs::PermissionsExt;
+    if metadata.permissions().mode() & 0o111 == 0 {
+        return ProgramFileType::NotExecutable;
+    }
+    let magic = read_magic(path);
+    if magic.starts_with(b"MZ") {
+        // Only runnable through the WSL binfmt interop handler
+        ProgramFileType::WslInteropStub
+    } else if magic.starts_with(b"#!") {
+        ProgramFileType::Script
+    } else {
+        ProgramFileType::NativeExecutable
+    }
+}
+
+/// `FileVersion` from the fixed version resource of a Windows binary
+#[cfg(windows)]
+fn file_version(path: &std::path::Path) -> Option<String> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::{
+        GetFileVersionInfoSizeW, GetFileVersionInfoW, VerQueryValueW, VS_FIXEDFILEINFO,
+    };
+
+    let wide: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let mut handle = 0u32;
+    let size = unsafe { GetFileVersionInfoSizeW(wide.as_ptr(), &mut handle) };
+    if size == 0 {
+        return None;
+    }
+    let mut data = vec![0u8; size as usize];
+    if unsafe { GetFileVersionInfoW(wide.as_ptr(), 0, size, data.as_mut_ptr().cast()) } == 0 {
+        return None;
+    }
+    let root: Vec<u16> = "\\".encode_utf16().chain(std::iter::once(0)).collect();
+    let mut info: *mut std::ffi::c_void = std::ptr::null_mut();
+    let mut len = 0u32;
+    let found = unsafe { VerQueryValueW(data.as_ptr().cast(), root.as_ptr(), &mut info, &mut len) };
+    if found == 0 || info.is_null() || (len as usize) < std::mem::size_of::<VS_FIXEDFILEINFO>() {
+        return None;
+    }
+    // Safety: VerQueryValueW points `info` into `data`, which is still alive
+    let fixed = unsafe { &*(info as *const VS_FIXEDFILEINFO) };
+    Some(format!(
+        "{}.{}.{}.{}",
+        fixed.dwFileVersionMS >> 16,
+        fixed.dwFileVersionMS & 0xffff,
+        fixed.dwFileVersionLS >> 16,
+        fixed.dwFileVersionLS & 0xffff
+    ))
+}
+
+#[cfg(not(windows))]
+fn file_version(_path: &std::path::Path) -> Option<String> {
+    None
+}
+
+/// A file on PATH named like the program being looked up
+struct PathMatch {
+    path: std::path::PathBuf,
+    metadata: std::fs::Metadata,
+    /// Whether a bare program name can start it, see [`candidate_names`]
+    runnable: bool,
+}
+
+/// Every file named after `program` in the directories of `path_var`, in
+/// PATH order and without duplicates. Within a directory the names are
+/// probed in the order [`candidate_names`] gives.
+fn path_matches(program: &str, path_var: &std::ffi::OsStr) -> Vec<PathMatch> {
+    let names = candidate_names(program);
+    let mut matches: Vec<PathMatch> = Vec::new();
+    for dir in std::env::split_paths(path_var) {
+        if dir.as_os_str().is_empty() {
+            continue;
+        }
+        for (name, runnable) in &names {
+            let path = dir.join(name);
+            let Ok(metadata) = std::fs::metadata(&path) else {
+                continue;
+            };
+            if metadata.is_file() && !matches.iter().any(|m| m.path == path) {
+                matches.push(PathMatch {
+                    path,
+                    metadata,
+                    runnable: *runnable,
+                });
+            }
+        }
+    }
+    matches
+}
+
+/// Every match for `program` across the directories of `path_var`, in PATH
+/// order, with what kind of file each is and which one a bare program name
+/// starts. PATHEXT decides the order and runnability on Windows.
+pub fn which_detailed_in(program: &str, path_var: &std::ffi::OsStr) -> Vec<ProgramCandidate> {
+    let mut selected = false;
+    path_matches(program, path_var)
+        .into_iter()
+        .map(|found| {
+            let is_symlink =
+                std::fs::symlink_metadata(&found.path).is_ok_and(|m| m.file_type().is_symlink());
+            let symlink_target = if is_symlink {
+                std::fs::canonicalize(&found.path)
+                    .ok()
+                    .map(|target| target.display().to_string())
+            } else {
+                None
+            };
+            let file_type = classify_program_file(&found.path, &found.metadata);
+            let is_winner =
+                !selected && found.runnable && file_type != ProgramFileType::NotExecutable;
+            selected |= is_winner;
+            ProgramCandidate {
+                path: found.path.display().to_string(),
+                file_type,
+                symlink_target,
+                file_version: match file_type {
+                    ProgramFileType::NativeExecutable => file_version(&found.path),
+                    _ => None,
+                },
+                selected: is_winner,
+            }
+        })
+        .collect()
+}
+
+/// [`which_detailed_in`] over the current process PATH.
+pub fn which_detailed(program: &str) -> Vec<ProgramCandidate> {
+    match std::env::var_os("PATH") {
+        Some(path_var) => which_detailed_in(program, &path_var),
+        None => Vec::new(),
+    }
+}
+
 pub fn is_program_available(program: &str) -> bool {
     std::process::Command::new(program)
         .arg("--version")
@@ -394,6 +723,37 @@ async fn test_execute_echo() {
         assert!(output.stdout.contains("hello"));
     }
 
+    #[tokio::test]
+    async fn test_execute_with_streaming_tracked_reports_pid() {
+        let mut reported = None;
+        #[cfg(windows)]
+        let output = execute_with_streaming_tracked(
+            "cmd",
+            &["/C", "echo", "tracked"],
+            None,
+            |pid| reported = pid,
+            |_| {},
+            |_| {},
+        )
+        .await
+        .unwrap();
+
+        #[cfg(not(windows))]
+        let output = execute_with_streaming_tracked(
+            "echo",
+            &["tracked"],
+            None,
+            |pid| reported = pid,
+            |_| {},
+            |_| {},
+        )
+        .await
+        .unwrap();
+
+        assert!(output.stdout.contains("tracked"));
+        assert!(reported.is_some_and(|pid| pid > 0));
+    }
+
     #[tokio::test]
     async fn test_execute_shell() {
         let output = execute_shell("echo test", None).await.unwrap();
@@ -412,6 +772,118 @@ async fn test_which() {
         assert!(result.is_some());
     }
 
+    #[test]
+    fn test_which_all_in_returns_shadowed_matches() {
+        let first = tempfile::tempdir().unwrap();
+        let empty = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        #[cfg(windows)]
+        let name = "tool.exe";
+        #[cfg(not(windows))]
+        let name = "tool";
+        std::fs::write(first.path().join(name), "").unwrap();
+        std::fs::write(second.path().join(name), "").unwrap();
+
+        let path_var =
+            std::env::join_paths([first.path(), empty.path(), second.path(), first.path()])
+                .unwrap();
+        let matches = which_all_in("tool", &path_var);
+        assert_eq!(
+            matches,
+            vec![first.path().join(name), second.path().join(name)]
+        );
+        assert!(which_all_in("missing-tool", &path_var).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_which_detailed_in_selects_first_executable() {
+        use std::os::unix::fs::PermissionsExt;
+        let plain = tempfile::tempdir().unwrap();
+        let script = tempfile::tempdir().unwrap();
+        let linked = tempfile::tempdir().unwrap();
+        std::fs::write(plain.path().join("tool"), "data").unwrap();
+        std::fs::write(script.path().join("tool"), "#!/bin/sh\necho hi\n").unwrap();
+        std::fs::set_permissions(
+            script.path().join("tool"),
+            std::fs::Permissions::from_mode(0o755),
+        )
+        .unwrap();
+        std::os::unix::fs::symlink(script.path().join("tool"), linked.path().join("tool")).unwrap();
+
+        let path_var = std::env::join_paths([plain.path(), script.path(), linked.path()]).unwrap();
+        let matches = which_detailed_in("tool", &path_var);
+        assert_eq!(matches.len(), 3);
+        assert_eq!(matches[0].file_type, ProgramFileType::NotExecutable);
+        assert!(!matches[0].selected);
+        assert_eq!(matches[1].file_type, ProgramFileType::Script);
+        assert!(matches[1].selected);
+        assert!(!matches[2].selected);
+        assert_eq!(
+            matches[2].symlink_target.as_deref(),
+            Some(
+                std::fs::canonicalize(script.path().join("tool"))
+                    .unwrap()
+                    .to_string_lossy()
+                    .as_ref()
+            )
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_windows_candidate_names_follow_pathext() {
+        let pathext = vec![".com".to_string(), ".exe".to_string(), ".cmd".to_string()];
+        let names = windows_candidate_names("node", &pathext);
+        assert_eq!(
+            names,
+            vec![
+                ("node.com".to_string(), true),
+                ("node.exe".to_string(), true),
+                ("node.cmd".to_string(), true),
+                ("node".to_string(), false),
+                ("node.ps1".to_string(), false),
+            ]
+        );
+
+        assert_eq!(
+            windows_candidate_names("node.CMD", &pathext),
+            vec![("node.CMD".to_string(), true)]
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_which_detailed_in_windows_pathext_ordering() {
+        let scripts = tempfile::tempdir().unwrap();
+        let tools = tempfile::tempdir().unwrap();
+        std::fs::write(scripts.path().join("node.ps1"), "").unwrap();
+        std::fs::write(scripts.path().join("node"), "#!/bin/sh\n").unwrap();
+        std::fs::write(tools.path().join("node.cmd"), "@echo off\r\n").unwrap();
+        std::fs::write(tools.path().join("node.exe"), "MZ").unwrap();
+
+        let path_var = std::env::join_paths([scripts.path(), tools.path()]).unwrap();
+        let matches = which_detailed_in("node", &path_var);
+        let names: Vec<String> = matches
+            .iter()
+            .map(|m| {
+                std::path::Path::new(&m.path)
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+        // Directory order first, PATHEXT order (.EXE before .CMD) within one
+        assert_eq!(names, vec!["node", "node.ps1", "node.exe", "node.cmd"]);
+        assert_eq!(matches[0].file_type, ProgramFileType::Script);
+        assert_eq!(matches[1].file_type, ProgramFileType::PowerShellScript);
+        assert_eq!(matches[2].file_type, ProgramFileType::NativeExecutable);
+        assert_eq!(matches[3].file_type, ProgramFileType::CmdWrapper);
+        let selected: Vec<bool> = matches.iter().map(|m| m.selected).collect();
+        assert_eq!(selected, vec![false, false, true, false]);
+    }
+
     #[test]
     fn test_process_options_defaults() {
         let opts = ProcessOptions::default();
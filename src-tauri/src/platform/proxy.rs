@@ -1,13 +1,38 @@
 use crate::config::Settings;
 use log::{debug, info, warn};
 use reqwest::Client;
+use serde::Serialize;
+use std::collections::HashMap;
 use std::sync::RwLock;
 use std::time::Duration;
 
 static SHARED_CLIENT: std::sync::OnceLock<RwLock<Client>> = std::sync::OnceLock::new();
+static PROVIDER_CLIENTS: std::sync::OnceLock<RwLock<HashMap<String, Client>>> =
+    std::sync::OnceLock::new();
 
 const USER_AGENT: &str = "CogniaLauncher/0.1.0";
 
+/// Per-provider override value that bypasses the global proxy.
+pub const DIRECT_PROXY: &str = "direct";
+
+/// Where the effective proxy for a request came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProxySource {
+    Provider,
+    Global,
+    None,
+}
+
+/// Proxy resolved for a provider after applying overrides.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveProxy {
+    /// Proxy URL to use, or `None` for a direct connection.
+    pub proxy_url: Option<String>,
+    pub source: ProxySource,
+}
+
 fn normalize_proxy_url(proxy_url: Option<&str>) -> Option<String> {
     let url = proxy_url?.trim();
     if url.is_empty() {
@@ -55,22 +80,93 @@ pub fn build_proxy(proxy_url: Option<&str>, no_proxy: Option<&str>) -> Option<re
     }
 }
 
+/// Override key shared by providers that talk to the same registry, so a
+/// single `npm` or `pypi` entry covers every client of that registry.
+fn provider_proxy_group(provider: &str) -> Option<&'static str> {
+    match provider {
+        "npm" | "pnpm" | "yarn" | "bun" => Some("npm"),
+        "pypi" | "pip" | "pipx" | "uv" | "poetry" => Some("pypi"),
+        "crates" | "cargo" => Some("crates"),
+        _ => None,
+    }
+}
+
+/// Candidate override keys for a provider, most specific first.
+fn provider_proxy_keys(provider: &str) -> Vec<String> {
+    let id = provider.trim().to_lowercase();
+    let mut keys = Vec::with_capacity(2);
+    if let Some(group) = provider_proxy_group(&id) {
+        if group != id {
+            keys.push(id.clone());
+        }
+        keys.push(group.to_string());
+    } else if !id.is_empty() {
+        keys.push(id);
+    }
+    keys
+}
+
+/// Resolve the proxy a provider should use: its own override (or its
+/// registry group's), then the global proxy.
+pub fn effective_proxy(settings: &Settings, provider: Option<&str>) -> EffectiveProxy {
+    let override_value = provider.and_then(|p| {
+        provider_proxy_keys(p)
+            .into_iter()
+            .find_map(|key| settings.network.provider_proxies.get(&key))
+    });
+
+    if let Some(value) = override_value {
+        let proxy_url = if value.trim().eq_ignore_ascii_case(DIRECT_PROXY) {
+            None
+        } else {
+            normalize_proxy_url(Some(value))
+        };
+        return EffectiveProxy {
+            proxy_url,
+            source: ProxySource::Provider,
+        };
+    }
+
+    match normalize_proxy_url(settings.network.proxy.as_deref()) {
+        Some(url) => EffectiveProxy {
+            proxy_url: Some(url),
+            source: ProxySource::Global,
+        },
+        None => EffectiveProxy {
+            proxy_url: None,
+            source: ProxySource::None,
+        },
+    }
+}
+
 /// Build a fully configured `reqwest::Client` from application settings.
 ///
 /// Applies proxy, no_proxy, security settings (certificate verification),
 /// timeout, and user-agent.
 pub fn build_client(settings: &Settings) -> Client {
+    build_client_with_proxy(settings, &effective_proxy(settings, None))
+}
+
+/// Build a client that routes through the effective proxy for `provider`.
+pub fn build_client_for_provider(settings: &Settings, provider: &str) -> Client {
+    build_client_with_proxy(settings, &effective_proxy(settings, Some(provider)))
+}
+
+fn build_client_with_proxy(settings: &Settings, effective: &EffectiveProxy) -> Client {
     let mut builder = Client::builder()
         .user_agent(USER_AGENT)
         .timeout(Duration::from_secs(settings.network.timeout))
         .connect_timeout(Duration::from_secs(settings.network.timeout));
 
-    // Apply proxy configuration
-    if let Some(proxy) = build_proxy(
-        settings.network.proxy.as_deref(),
+    // Apply proxy configuration. A direct override must also ignore proxy
+    // environment variables, which reqwest honours by default.
+    match build_proxy(
+        effective.proxy_url.as_deref(),
         settings.network.no_proxy.as_deref(),
     ) {
-        builder = builder.proxy(proxy);
+        Some(proxy) => builder = builder.proxy(proxy),
+        None if effective.source == ProxySource::Provider => builder = builder.no_proxy(),
+        None => {}
     }
 
     // Apply security settings
@@ -111,10 +207,49 @@ pub fn get_shared_client() -> Client {
     }
 }
 
+/// Get the client built for a provider's proxy override, if one is configured.
+pub fn provider_override_client(provider: &str) -> Option<Client> {
+    let lock = PROVIDER_CLIENTS.get()?;
+    let clients = lock.read().unwrap_or_else(|e| {
+        warn!("Provider client lock poisoned, returning inner: {}", e);
+        e.into_inner()
+    });
+    provider_proxy_keys(provider)
+        .iter()
+        .find_map(|key| clients.get(key).cloned())
+}
+
+/// Get the HTTP client a provider should use, falling back to the shared
+/// client when no override applies.
+pub fn get_client_for_provider(provider: &str) -> Client {
+    provider_override_client(provider).unwrap_or_else(get_shared_client)
+}
+
 /// Initialize or rebuild the global shared HTTP client from settings.
 ///
 /// Called at app startup and whenever network/security settings change.
+/// Per-provider override clients are rebuilt alongside it.
 pub fn rebuild_shared_client(settings: &Settings) {
+    let provider_clients: HashMap<String, Client> = settings
+        .network
+        .provider_proxies
+        .keys()
+        .map(|key| {
+            let key = key.trim().to_lowercase();
+            let client = build_client_for_provider(settings, &key);
+            (key, client)
+        })
+        .collect();
+    match PROVIDER_CLIENTS.get() {
+        Some(lock) => match lock.write() {
+            Ok(mut guard) => *guard = provider_clients,
+            Err(e) => warn!("Failed to acquire write lock for provider clients: {}", e),
+        },
+        None => {
+            let _ = PROVIDER_CLIENTS.set(RwLock::new(provider_clients));
+        }
+    }
+
     let client = build_client(settings);
     match SHARED_CLIENT.get() {
         Some(lock) => match lock.write() {
@@ -244,6 +379,84 @@ fn test_build_proxy_rejects_unsupported_scheme() {
         assert!(proxy.is_none());
     }
 
+    #[test]
+    fn test_effective_proxy_prefers_provider_override() {
+        let mut settings = Settings::default();
+        settings.network.proxy = Some("http://global.example.com:8080".into());
+        settings
+            .network
+            .provider_proxies
+            .insert("github".into(), "socks5://127.0.0.1:1080".into());
+
+        let github = effective_proxy(&settings, Some("GitHub"));
+        assert_eq!(github.source, ProxySource::Provider);
+        assert_eq!(github.proxy_url.as_deref(), Some("socks5://127.0.0.1:1080"));
+
+        let gitlab = effective_proxy(&settings, Some("gitlab"));
+        assert_eq!(gitlab.source, ProxySource::Global);
+        assert_eq!(
+            gitlab.proxy_url.as_deref(),
+            Some("http://global.example.com:8080")
+        );
+    }
+
+    #[test]
+    fn test_effective_proxy_uses_registry_group_and_direct() {
+        let mut settings = Settings::default();
+        settings.network.proxy = Some("http://global.example.com:8080".into());
+        settings
+            .network
+            .provider_proxies
+            .insert("npm".into(), DIRECT_PROXY.into());
+        settings
+            .network
+            .provider_proxies
+            .insert("pypi".into(), "http://pypi-proxy:3128".into());
+        settings
+            .network
+            .provider_proxies
+            .insert("uv".into(), "socks5h://uv-proxy:1080".into());
+
+        let yarn = effective_proxy(&settings, Some("yarn"));
+        assert_eq!(yarn.source, ProxySource::Provider);
+        assert!(yarn.proxy_url.is_none());
+
+        let pip = effective_proxy(&settings, Some("pip"));
+        assert_eq!(pip.proxy_url.as_deref(), Some("http://pypi-proxy:3128"));
+
+        let uv = effective_proxy(&settings, Some("uv"));
+        assert_eq!(uv.proxy_url.as_deref(), Some("socks5h://uv-proxy:1080"));
+
+        let none = effective_proxy(&Settings::default(), Some("npm"));
+        assert_eq!(none.source, ProxySource::None);
+    }
+
+    #[test]
+    fn test_provider_proxy_keys_specific_before_group() {
+        assert_eq!(provider_proxy_keys("Yarn"), vec!["yarn", "npm"]);
+        assert_eq!(provider_proxy_keys("npm"), vec!["npm"]);
+        assert_eq!(provider_proxy_keys("github"), vec!["github"]);
+        assert!(provider_proxy_keys("  ").is_empty());
+    }
+
+    #[test]
+    fn test_build_client_for_provider_with_overrides() {
+        let mut settings = Settings::default();
+        settings.network.proxy = Some("http://global.example.com:8080".into());
+        settings
+            .network
+            .provider_proxies
+            .insert("github".into(), "socks5://127.0.0.1:1080".into());
+        settings
+            .network
+            .provider_proxies
+            .insert("gitlab".into(), DIRECT_PROXY.into());
+        for provider in ["github", "gitlab", "npm"] {
+            let client = build_client_for_provider(&settings, provider);
+            assert!(client.get("https://example.com").build().is_ok());
+        }
+    }
+
     #[test]
     fn test_build_proxy_trims_url_whitespace() {
         let proxy = build_proxy(Some("  http://proxy.example.com:8080  "), None);
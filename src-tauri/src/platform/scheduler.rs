@@ -0,0 +1,434 @@
+//! OS-level scheduled background update checks.
+//!
+//! Registers a recurring task that launches Cognia with
+//! [`BACKGROUND_UPDATE_CHECK_FLAG`] even when the app is not running: a
+//! launchd agent on macOS, a Task Scheduler entry on Windows and a systemd user
+//! timer on Linux. The registered executable is recorded so the task can be
+//! re-registered when the binary moves (e.g. after a self-update).
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::process::{self, ProcessOptions};
+
+/// Command-line flag the scheduled task launches the app with
+pub const BACKGROUND_UPDATE_CHECK_FLAG: &str = "--background-update-check";
+
+const LAUNCHD_LABEL: &str = "com.cognia.launcher.update-check";
+const SCHTASKS_TASK_NAME: &str = "CogniaLauncher\\UpdateCheck";
+const SYSTEMD_UNIT_NAME: &str = "cognia-update-check";
+const SCHEDULE_RECORD_FILE: &str = "update-schedule.json";
+
+/// How often the scheduled check runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScheduleInterval {
+    Daily,
+    Weekly,
+}
+
+impl ScheduleInterval {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "daily" => Some(Self::Daily),
+            "weekly" => Some(Self::Weekly),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Daily => "daily",
+            Self::Weekly => "weekly",
+        }
+    }
+
+    pub fn as_secs(self) -> u64 {
+        match self {
+            Self::Daily => 24 * 60 * 60,
+            Self::Weekly => 7 * 24 * 60 * 60,
+        }
+    }
+}
+
+/// What Cognia registered, persisted next to the settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledTaskRecord {
+    pub interval: ScheduleInterval,
+    pub executable: String,
+    pub registered_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleStatus {
+    /// Scheduler backend for this platform (`launchd`, `schtasks`, `systemd`)
+    pub backend: String,
+    /// Whether the OS currently has the task registered
+    pub registered: bool,
+    pub interval: Option<ScheduleInterval>,
+    pub executable: Option<String>,
+    /// False when the registered executable no longer matches the running binary
+    pub executable_current: bool,
+    pub registered_at: Option<String>,
+}
+
+pub fn backend_name() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "launchd"
+    } else if cfg!(windows) {
+        "schtasks"
+    } else {
+        "systemd"
+    }
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// launchd agent definition
+pub fn launchd_plist(executable: &Path, interval: ScheduleInterval) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{label}</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>{exe}</string>
+        <string>{flag}</string>
+    </array>
+    <key>StartInterval</key>
+    <integer>{secs}</integer>
+    <key>RunAtLoad</key>
+    <false/>
+    <key>ProcessType</key>
+    <string>Background</string>
+</dict>
+</plist>
+"#,
+        label = LAUNCHD_LABEL,
+        exe = xml_escape(&executable.display().to_string()),
+        flag = BACKGROUND_UPDATE_CHECK_FLAG,
+        secs = interval.as_secs(),
+    )
+}
+
+/// systemd user service that runs one check
+pub fn systemd_service(executable: &Path) -> String {
+    let exe = executable
+        .display()
+        .to_string()
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"");
+    format!(
+        "[Unit]\nDescription=CogniaLauncher background update check\n\n[Service]\nType=oneshot\nExecStart=\"{}\" {}\n",
+        exe, BACKGROUND_UPDATE_CHECK_FLAG
+    )
+}
+
+/// systemd user timer; `Persistent` catches up on runs missed while powered off
+pub fn systemd_timer(interval: ScheduleInterval) -> String {
+    format!(
+        "[Unit]\nDescription=Run CogniaLauncher update check {}\n\n[Timer]\nOnCalendar={}\nPersistent=true\nRandomizedDelaySec=1h\n\n[Install]\nWantedBy=timers.target\n",
+        interval.as_str(),
+        interval.as_str()
+    )
+}
+
+/// Arguments for `schtasks /Create`
+pub fn schtasks_create_args(executable: &Path, interval: ScheduleInterval) -> Vec<String> {
+    let schedule = match interval {
+        ScheduleInterval::Daily => "DAILY",
+        ScheduleInterval::Weekly => "WEEKLY",
+    };
+    vec![
+        "/Create".into(),
+        "/F".into(),
+        "/TN".into(),
+        SCHTASKS_TASK_NAME.into(),
+        "/TR".into(),
+        format!(
+            "\"{}\" {}",
+            executable.display(),
+            BACKGROUND_UPDATE_CHECK_FLAG
+        ),
+        "/SC".into(),
+        schedule.into(),
+        "/ST".into(),
+        "10:00".into(),
+    ]
+}
+
+fn record_path() -> CogniaResult<PathBuf> {
+    crate::platform::fs::get_config_dir()
+        .map(|dir| dir.join(SCHEDULE_RECORD_FILE))
+        .ok_or_else(|| CogniaError::Config("Cannot resolve config directory".into()))
+}
+
+pub async fn load_record() -> Option<ScheduledTaskRecord> {
+    let content = tokio::fs::read_to_string(record_path().ok()?).await.ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+async fn save_record(record: &ScheduledTaskRecord) -> CogniaResult<()> {
+    let path = record_path()?;
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let content =
+        serde_json::to_string_pretty(record).map_err(|e| CogniaError::Parse(e.to_string()))?;
+    tokio::fs::write(&path, content).await?;
+    Ok(())
+}
+
+async fn remove_record() {
+    if let Ok(path) = record_path() {
+        let _ = tokio::fs::remove_file(path).await;
+    }
+}
+
+async fn run_scheduler_command(program: &str, args: &[&str]) -> CogniaResult<String> {
+    let opts = ProcessOptions::new().with_timeout(std::time::Duration::from_secs(30));
+    let output = process::execute(program, args, Some(opts))
+        .await
+        .map_err(|e| CogniaError::Internal(format!("{} failed: {}", program, e)))?;
+    if !output.success {
+        let detail = if output.stderr.trim().is_empty() {
+            output.stdout.trim().to_string()
+        } else {
+            output.stderr.trim().to_string()
+        };
+        return Err(CogniaError::Internal(format!(
+            "{} {} exited with code {}: {}",
+            program,
+            args.first().copied().unwrap_or_default(),
+            output.exit_code,
+            detail
+        )));
+    }
+    Ok(output.stdout)
+}
+
+fn launchd_plist_path() -> CogniaResult<PathBuf> {
+    crate::platform::fs::get_home_dir()
+        .map(|home| {
+            home.join("Library")
+                .join("LaunchAgents")
+                .join(format!("{}.plist", LAUNCHD_LABEL))
+        })
+        .ok_or_else(|| CogniaError::Config("Cannot resolve home directory".into()))
+}
+
+fn systemd_user_dir() -> CogniaResult<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| crate::platform::fs::get_home_dir().map(|home| home.join(".config")))
+        .map(|config| config.join("systemd").join("user"))
+        .ok_or_else(|| CogniaError::Config("Cannot resolve systemd user directory".into()))
+}
+
+async fn install_os_task(executable: &Path, interval: ScheduleInterval) -> CogniaResult<()> {
+    if cfg!(target_os = "macos") {
+        let plist_path = launchd_plist_path()?;
+        if let Some(parent) = plist_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let plist = plist_path.display().to_string();
+        // Unload any previous definition so launchd picks up the new one
+        let _ = run_scheduler_command("launchctl", &["unload", &plist]).await;
+        tokio::fs::write(&plist_path, launchd_plist(executable, interval)).await?;
+        run_scheduler_command("launchctl", &["load", "-w", &plist]).await?;
+    } else if cfg!(windows) {
+        let args = schtasks_create_args(executable, interval);
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        run_scheduler_command("schtasks", &args).await?;
+    } else {
+        let unit_dir = systemd_user_dir()?;
+        tokio::fs::create_dir_all(&unit_dir).await?;
+        tokio::fs::write(
+            unit_dir.join(format!("{}.service", SYSTEMD_UNIT_NAME)),
+            systemd_service(executable),
+        )
+        .await?;
+        tokio::fs::write(
+            unit_dir.join(format!("{}.timer", SYSTEMD_UNIT_NAME)),
+            systemd_timer(interval),
+        )
+        .await?;
+        let timer = format!("{}.timer", SYSTEMD_UNIT_NAME);
+        run_scheduler_command("systemctl", &["--user", "daemon-reload"]).await?;
+        run_scheduler_command("systemctl", &["--user", "enable", "--now", &timer]).await?;
+    }
+    Ok(())
+}
+
+async fn remove_os_task() -> CogniaResult<()> {
+    if cfg!(target_os = "macos") {
+        let plist_path = launchd_plist_path()?;
+        if plist_path.exists() {
+            let plist = plist_path.display().to_string();
+            let _ = run_scheduler_command("launchctl", &["unload", "-w", &plist]).await;
+            tokio::fs::remove_file(&plist_path).await?;
+        }
+    } else if cfg!(windows) {
+        if os_task_registered().await {
+            run_scheduler_command("schtasks", &["/Delete", "/F", "/TN", SCHTASKS_TASK_NAME])
+                .await?;
+        }
+    } else {
+        let unit_dir = systemd_user_dir()?;
+        let timer = format!("{}.timer", SYSTEMD_UNIT_NAME);
+        let _ = run_scheduler_command("systemctl", &["--user", "disable", "--now", &timer]).await;
+        for ext in ["timer", "service"] {
+            let path = unit_dir.join(format!("{}.{}", SYSTEMD_UNIT_NAME, ext));
+            if path.exists() {
+                tokio::fs::remove_file(&path).await?;
+            }
+        }
+        let _ = run_scheduler_command("systemctl", &["--user", "daemon-reload"]).await;
+    }
+    Ok(())
+}
+
+/// Ask the OS whether the task is currently registered
+pub async fn os_task_registered() -> bool {
+    if cfg!(target_os = "macos") {
+        launchd_plist_path().is_ok_and(|p| p.exists())
+            && run_scheduler_command("launchctl", &["list", LAUNCHD_LABEL])
+                .await
+                .is_ok()
+    } else if cfg!(windows) {
+        run_scheduler_command("schtasks", &["/Query", "/TN", SCHTASKS_TASK_NAME])
+            .await
+            .is_ok()
+    } else {
+        let timer = format!("{}.timer", SYSTEMD_UNIT_NAME);
+        run_scheduler_command("systemctl", &["--user", "is-enabled", &timer])
+            .await
+            .is_ok_and(|out| out.trim() == "enabled")
+    }
+}
+
+/// Register (or replace) the scheduled task for `executable`
+pub async fn register(
+    executable: &Path,
+    interval: ScheduleInterval,
+) -> CogniaResult<ScheduledTaskRecord> {
+    install_os_task(executable, interval).await?;
+    let record = ScheduledTaskRecord {
+        interval,
+        executable: executable.display().to_string(),
+        registered_at: chrono::Utc::now().to_rfc3339(),
+    };
+    save_record(&record).await?;
+    Ok(record)
+}
+
+/// Remove the scheduled task. Returns whether one had been registered.
+pub async fn unregister() -> CogniaResult<bool> {
+    let had_record = load_record().await.is_some();
+    remove_os_task().await?;
+    remove_record().await;
+    Ok(had_record)
+}
+
+pub async fn status(current_exe: Option<&Path>) -> ScheduleStatus {
+    let record = load_record().await;
+    let registered = os_task_registered().await;
+    let executable_current = match (&record, current_exe) {
+        (Some(record), Some(exe)) => Path::new(&record.executable) == exe,
+        _ => false,
+    };
+    ScheduleStatus {
+        backend: backend_name().to_string(),
+        registered,
+        interval: record.as_ref().map(|r| r.interval),
+        executable: record.as_ref().map(|r| r.executable.clone()),
+        executable_current,
+        registered_at: record.map(|r| r.registered_at),
+    }
+}
+
+/// Re-register the task when the recorded executable differs from the
+/// running binary. Returns the new record if re-registration happened.
+pub async fn refresh_if_moved(current_exe: &Path) -> CogniaResult<Option<ScheduledTaskRecord>> {
+    let Some(record) = load_record().await else {
+        return Ok(None);
+    };
+    if Path::new(&record.executable) == current_exe {
+        return Ok(None);
+    }
+    register(current_exe, record.interval).await.map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval_parse() {
+        assert_eq!(
+            ScheduleInterval::parse("Weekly"),
+            Some(ScheduleInterval::Weekly)
+        );
+        assert_eq!(
+            ScheduleInterval::parse(" daily "),
+            Some(ScheduleInterval::Daily)
+        );
+        assert_eq!(ScheduleInterval::parse("hourly"), None);
+        assert_eq!(ScheduleInterval::Weekly.as_secs(), 604_800);
+    }
+
+    #[test]
+    fn test_launchd_plist_escapes_executable() {
+        let plist = launchd_plist(
+            Path::new("/Applications/A&B.app/Contents/MacOS/cognia"),
+            ScheduleInterval::Weekly,
+        );
+        assert!(plist.contains("<string>/Applications/A&amp;B.app/Contents/MacOS/cognia</string>"));
+        assert!(plist.contains(&format!(
+            "<string>{}</string>",
+            BACKGROUND_UPDATE_CHECK_FLAG
+        )));
+        assert!(plist.contains("<integer>604800</integer>"));
+    }
+
+    #[test]
+    fn test_systemd_units() {
+        let service = systemd_service(Path::new("/opt/Cognia Launcher/cognia"));
+        assert!(
+            service.contains("ExecStart=\"/opt/Cognia Launcher/cognia\" --background-update-check")
+        );
+        let timer = systemd_timer(ScheduleInterval::Daily);
+        assert!(timer.contains("OnCalendar=daily"));
+        assert!(timer.contains("Persistent=true"));
+    }
+
+    #[test]
+    fn test_schtasks_create_args() {
+        let args = schtasks_create_args(
+            Path::new("C:\\Program Files\\Cognia\\cognia.exe"),
+            ScheduleInterval::Weekly,
+        );
+        let tr = args.iter().position(|a| a == "/TR").unwrap();
+        assert_eq!(
+            args[tr + 1],
+            "\"C:\\Program Files\\Cognia\\cognia.exe\" --background-update-check"
+        );
+        let sc = args.iter().position(|a| a == "/SC").unwrap();
+        assert_eq!(args[sc + 1], "WEEKLY");
+        assert!(args.contains(&"/F".to_string()));
+    }
+}
@@ -0,0 +1,767 @@
+//! Secret storage for tokens and credentials.
+//!
+//! Values live in the OS keychain (Windows Credential Manager, macOS
+//! Keychain, the Secret Service via `secret-tool` on Linux). When no
+//! keychain is reachable they are sealed with AES-256-GCM into
+//! `secrets.enc.json` under a key kept in `secrets.key`, next to it.
+//!
+//! `secrets.index.json` records which secrets exist and where, without
+//! their values, so secrets can be listed and referenced from exports.
+
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::fs;
+use once_cell::sync::Lazy;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tokio::sync::{Mutex, OnceCell};
+
+/// Service name the keychain entries are filed under
+pub const KEYCHAIN_SERVICE: &str = "CogniaLauncher";
+const INDEX_FILE: &str = "secrets.index.json";
+const ENCRYPTED_FILE: &str = "secrets.enc.json";
+const KEY_FILE: &str = "secrets.key";
+const MAX_NAME_LEN: usize = 128;
+static STORE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+static GLOBAL_STORE: Lazy<SecretStore> = Lazy::new(SecretStore::default);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretBackend {
+    Keychain,
+    EncryptedFile,
+}
+
+/// A stored secret, without its value
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretRef {
+    pub namespace: String,
+    pub key: String,
+    pub backend: SecretBackend,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SecretIndex {
+    /// Keyed by `namespace/key`
+    entries: BTreeMap<String, SecretRef>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SealedValue {
+    nonce: String,
+    ciphertext: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SealedFile {
+    entries: BTreeMap<String, SealedValue>,
+}
+
+/// The store rooted at the config directory
+pub fn global() -> &'static SecretStore {
+    &GLOBAL_STORE
+}
+
+pub struct SecretStore {
+    dir: PathBuf,
+    backend: OnceCell<SecretBackend>,
+}
+
+impl Default for SecretStore {
+    fn default() -> Self {
+        Self::new(fs::get_config_dir().unwrap_or_else(|| PathBuf::from(".")))
+    }
+}
+
+impl SecretStore {
+    /// A store that uses the keychain when one answers on first use
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            backend: OnceCell::new(),
+        }
+    }
+
+    /// A store that never touches the keychain
+    pub fn encrypted_file(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            backend: OnceCell::new_with(Some(SecretBackend::EncryptedFile)),
+        }
+    }
+
+    /// Where new secrets are written
+    pub async fn backend(&self) -> SecretBackend {
+        *self
+            .backend
+            .get_or_init(|| async {
+                if keychain::available().await {
+                    SecretBackend::Keychain
+                } else {
+                    log::info!("No OS keychain available, using the encrypted secrets file");
+                    SecretBackend::EncryptedFile
+                }
+            })
+            .await
+    }
+
+    pub async fn set(&self, namespace: &str, key: &str, value: &str) -> CogniaResult<SecretRef> {
+        let account = account_name(namespace, key)?;
+        if value.is_empty() {
+            return Err(CogniaError::Config("Secret value must not be empty".into()));
+        }
+        let backend = self.backend().await;
+
+        let _guard = STORE_LOCK.lock().await;
+        let mut index = self.load_index().await?;
+        let previous = index.entries.get(&account).map(|entry| entry.backend);
+
+        match backend {
+            SecretBackend::Keychain => keychain::set(&account, value).await?,
+            SecretBackend::EncryptedFile => self.seal(&account, value).await?,
+        }
+        if let Some(previous) = previous.filter(|previous| *previous != backend) {
+            self.remove_value(previous, &account).await?;
+        }
+
+        let entry = SecretRef {
+            namespace: namespace.to_string(),
+            key: key.to_string(),
+            backend,
+            updated_at: chrono::Utc::now().to_rfc3339(),
+        };
+        index.entries.insert(account, entry.clone());
+        self.save_index(&index).await?;
+        Ok(entry)
+    }
+
+    pub async fn get(&self, namespace: &str, key: &str) -> CogniaResult<Option<String>> {
+        let account = account_name(namespace, key)?;
+        let _guard = STORE_LOCK.lock().await;
+        let Some(entry) = self.load_index().await?.entries.remove(&account) else {
+            return Ok(None);
+        };
+        match entry.backend {
+            SecretBackend::Keychain => keychain::get(&account).await,
+            SecretBackend::EncryptedFile => self.unseal(&account).await,
+        }
+    }
+
+    pub async fn contains(&self, namespace: &str, key: &str) -> CogniaResult<bool> {
+        let account = account_name(namespace, key)?;
+        let _guard = STORE_LOCK.lock().await;
+        Ok(self.load_index().await?.entries.contains_key(&account))
+    }
+
+    /// Remove a secret. Returns false if it was not stored.
+    pub async fn delete(&self, namespace: &str, key: &str) -> CogniaResult<bool> {
+        let account = account_name(namespace, key)?;
+        let _guard = STORE_LOCK.lock().await;
+        let mut index = self.load_index().await?;
+        let Some(entry) = index.entries.remove(&account) else {
+            return Ok(false);
+        };
+        self.remove_value(entry.backend, &account).await?;
+        self.save_index(&index).await?;
+        Ok(true)
+    }
+
+    /// Secrets stored under `namespace`, sorted by key
+    pub async fn list(&self, namespace: &str) -> CogniaResult<Vec<SecretRef>> {
+        validate_name("namespace", namespace)?;
+        let _guard = STORE_LOCK.lock().await;
+        Ok(self
+            .load_index()
+            .await?
+            .entries
+            .into_values()
+            .filter(|entry| entry.namespace == namespace)
+            .collect())
+    }
+
+    async fn remove_value(&self, backend: SecretBackend, account: &str) -> CogniaResult<()> {
+        match backend {
+            SecretBackend::Keychain => {
+                keychain::delete(account).await?;
+            }
+            SecretBackend::EncryptedFile => {
+                let mut sealed = self.load_sealed().await?;
+                if sealed.entries.remove(account).is_some() {
+                    self.save_sealed(&sealed).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn load_index(&self) -> CogniaResult<SecretIndex> {
+        Ok(fs::read_json_with_backup(self.dir.join(INDEX_FILE))
+            .await?
+            .into_value("Secret index")
+            .unwrap_or_default())
+    }
+
+    async fn save_index(&self, index: &SecretIndex) -> CogniaResult<()> {
+        fs::atomic_write_json(self.dir.join(INDEX_FILE), index, true).await?;
+        Ok(())
+    }
+
+    async fn load_sealed(&self) -> CogniaResult<SealedFile> {
+        Ok(fs::read_json_with_backup(self.dir.join(ENCRYPTED_FILE))
+            .await?
+            .into_value("Encrypted secrets")
+            .unwrap_or_default())
+    }
+
+    async fn save_sealed(&self, sealed: &SealedFile) -> CogniaResult<()> {
+        let path = self.dir.join(ENCRYPTED_FILE);
+        fs::atomic_write_json(&path, sealed, true).await?;
+        restrict_permissions(&path).await
+    }
+
+    async fn seal(&self, account: &str, value: &str) -> CogniaResult<()> {
+        let key = self.file_key().await?;
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| CogniaError::Internal("Failed to generate a nonce".into()))?;
+
+        let mut in_out = value.as_bytes().to_vec();
+        key.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(account.as_bytes()),
+            &mut in_out,
+        )
+        .map_err(|_| CogniaError::Internal("Failed to encrypt secret".into()))?;
+
+        let mut sealed = self.load_sealed().await?;
+        sealed.entries.insert(
+            account.to_string(),
+            SealedValue {
+                nonce: hex::encode(nonce),
+                ciphertext: hex::encode(in_out),
+            },
+        );
+        self.save_sealed(&sealed).await
+    }
+
+    async fn unseal(&self, account: &str) -> CogniaResult<Option<String>> {
+        let Some(value) = self.load_sealed().await?.entries.remove(account) else {
+            return Ok(None);
+        };
+        let corrupt = || CogniaError::Config(format!("Stored secret '{}' is unreadable", account));
+
+        let nonce: [u8; NONCE_LEN] = hex::decode(&value.nonce)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(corrupt)?;
+        let mut in_out = hex::decode(&value.ciphertext).map_err(|_| corrupt())?;
+        let plain = self
+            .file_key()
+            .await?
+            .open_in_place(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(account.as_bytes()),
+                &mut in_out,
+            )
+            .map_err(|_| corrupt())?;
+        String::from_utf8(plain.to_vec())
+            .map(Some)
+            .map_err(|_| corrupt())
+    }
+
+    /// The file backend's key, created on first use
+    async fn file_key(&self) -> CogniaResult<LessSafeKey> {
+        let path = self.dir.join(KEY_FILE);
+        let bytes = if fs::exists(&path).await {
+            fs::read_file(&path).await?
+        } else {
+            let mut bytes = vec![0u8; AES_256_GCM.key_len()];
+            SystemRandom::new()
+                .fill(&mut bytes)
+                .map_err(|_| CogniaError::Internal("Failed to generate a key".into()))?;
+            fs::write_file_atomic(&path, &bytes).await?;
+            restrict_permissions(&path).await?;
+            bytes
+        };
+        let key = UnboundKey::new(&AES_256_GCM, &bytes)
+            .map_err(|_| CogniaError::Config(format!("Invalid secrets key at {:?}", path)))?;
+        Ok(LessSafeKey::new(key))
+    }
+}
+
+fn validate_name(what: &str, name: &str) -> CogniaResult<()> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | ':'));
+    if valid {
+        Ok(())
+    } else {
+        Err(CogniaError::Config(format!(
+            "Invalid secret {} '{}': use letters, digits, '.', '_', '-' or ':'",
+            what, name
+        )))
+    }
+}
+
+/// The keychain account a secret is filed under
+fn account_name(namespace: &str, key: &str) -> CogniaResult<String> {
+    validate_name("namespace", namespace)?;
+    validate_name("key", key)?;
+    Ok(format!("{}/{}", namespace, key))
+}
+
+/// Prefix of keychain values stored hex-encoded. `security -i` reads one
+/// command per line, so a value with a line break or another control
+/// character cannot be passed to it as it is.
+#[cfg(any(target_os = "macos", test))]
+const HEX_VALUE_PREFIX: &str = "cognia-hex:";
+
+#[cfg(any(target_os = "macos", test))]
+fn encode_keychain_value(value: &str) -> String {
+    if value.chars().any(char::is_control) || value.starts_with(HEX_VALUE_PREFIX) {
+        format!("{}{}", HEX_VALUE_PREFIX, hex::encode(value))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(any(target_os = "macos", test))]
+fn decode_keychain_value(account: &str, stored: &str) -> CogniaResult<String> {
+    let Some(encoded) = stored.strip_prefix(HEX_VALUE_PREFIX) else {
+        return Ok(stored.to_string());
+    };
+    hex::decode(encoded)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .ok_or_else(|| CogniaError::Config(format!("Stored secret '{}' is unreadable", account)))
+}
+
+#[cfg(unix)]
+async fn restrict_permissions(path: &Path) -> CogniaResult<()> {
+    use std::os::unix::fs::PermissionsExt;
+    tokio::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600)).await?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+async fn restrict_permissions(_path: &Path) -> CogniaResult<()> {
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+mod keychain {
+    use super::KEYCHAIN_SERVICE;
+    use crate::error::{CogniaError, CogniaResult};
+    use std::ffi::OsStr;
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Foundation::ERROR_NOT_FOUND;
+    use windows_sys::Win32::Security::Credentials::{
+        CredDeleteW, CredFree, CredReadW, CredWriteW, CREDENTIALW, CRED_PERSIST_LOCAL_MACHINE,
+        CRED_TYPE_GENERIC,
+    };
+
+    fn target_name(account: &str) -> Vec<u16> {
+        OsStr::new(&format!("{}:{}", KEYCHAIN_SERVICE, account))
+            .encode_wide()
+            .chain(std::iter::once(0))
+            .collect()
+    }
+
+    fn last_error(action: &str) -> CogniaError {
+        CogniaError::Internal(format!(
+            "Credential Manager failed to {}: {}",
+            action,
+            std::io::Error::last_os_error()
+        ))
+    }
+
+    fn is_not_found() -> bool {
+        std::io::Error::last_os_error().raw_os_error() == Some(ERROR_NOT_FOUND as i32)
+    }
+
+    pub async fn available() -> bool {
+        true
+    }
+
+    pub async fn set(account: &str, value: &str) -> CogniaResult<()> {
+        let mut target = target_name(account);
+        let mut blob = value.as_bytes().to_vec();
+        // SAFETY: every pointer in the struct outlives the call, and the
+        // fields left zeroed are documented as optional.
+        let written = unsafe {
+            let mut credential: CREDENTIALW = std::mem::zeroed();
+            credential.Type = CRED_TYPE_GENERIC;
+            credential.TargetName = target.as_mut_ptr();
+            credential.CredentialBlobSize = blob.len() as u32;
+            credential.CredentialBlob = blob.as_mut_ptr();
+            credential.Persist = CRED_PERSIST_LOCAL_MACHINE;
+            CredWriteW(&credential, 0)
+        };
+        if written == 0 {
+            return Err(last_error("store a secret"));
+        }
+        Ok(())
+    }
+
+    pub async fn get(account: &str) -> CogniaResult<Option<String>> {
+        let target = target_name(account);
+        let mut credential: *mut CREDENTIALW = std::ptr::null_mut();
+        // SAFETY: `credential` is only read after a successful call and is
+        // released with CredFree.
+        unsafe {
+            if CredReadW(target.as_ptr(), CRED_TYPE_GENERIC, 0, &mut credential) == 0 {
+                return if is_not_found() {
+                    Ok(None)
+                } else {
+                    Err(last_error("read a secret"))
+                };
+            }
+            let blob = std::slice::from_raw_parts(
+                (*credential).CredentialBlob,
+                (*credential).CredentialBlobSize as usize,
+            );
+            let value = String::from_utf8(blob.to_vec());
+            CredFree(credential as *const _);
+            value.map(Some).map_err(|_| {
+                CogniaError::Config(format!("Stored secret '{}' is not UTF-8", account))
+            })
+        }
+    }
+
+    pub async fn delete(account: &str) -> CogniaResult<bool> {
+        let target = target_name(account);
+        // SAFETY: `target` is a NUL-terminated wide string.
+        if unsafe { CredDeleteW(target.as_ptr(), CRED_TYPE_GENERIC, 0) } == 0 {
+            return if is_not_found() {
+                Ok(false)
+            } else {
+                Err(last_error("delete a secret"))
+            };
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod keychain {
+    use super::KEYCHAIN_SERVICE;
+    use crate::error::{CogniaError, CogniaResult};
+    use crate::platform::process::{self, ProcessOptions};
+    use std::time::Duration;
+
+    /// `security` exit code for a missing item
+    const ITEM_NOT_FOUND: i32 = 44;
+    const TIMEOUT: Duration = Duration::from_secs(15);
+
+    fn options() -> ProcessOptions {
+        ProcessOptions::new().with_timeout(TIMEOUT)
+    }
+
+    fn quote(value: &str) -> String {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+
+    pub async fn available() -> bool {
+        process::execute("security", &["default-keychain"], Some(options()))
+            .await
+            .is_ok_and(|output| output.success)
+    }
+
+    pub async fn set(account: &str, value: &str) -> CogniaResult<()> {
+        // Passed through `security -i` on stdin so the value never shows
+        // up in the process list.
+        let command = format!(
+            "add-generic-password -U -s {} -a {} -w {}\n",
+            quote(KEYCHAIN_SERVICE),
+            quote(account),
+            quote(&super::encode_keychain_value(value))
+        );
+        let output =
+            process::execute("security", &["-i"], Some(options().with_stdin(command))).await?;
+        if !output.success || !output.stderr.trim().is_empty() {
+            return Err(CogniaError::Internal(format!(
+                "Keychain rejected the secret: {}",
+                output.stderr.trim()
+            )));
+        }
+        Ok(())
+    }
+
+    pub async fn get(account: &str) -> CogniaResult<Option<String>> {
+        let output = process::execute(
+            "security",
+            &[
+                "find-generic-password",
+                "-s",
+                KEYCHAIN_SERVICE,
+                "-a",
+                account,
+                "-w",
+            ],
+            Some(options()),
+        )
+        .await?;
+        match output.exit_code {
+            0 => super::decode_keychain_value(account, output.stdout.trim_end_matches('\n'))
+                .map(Some),
+            ITEM_NOT_FOUND => Ok(None),
+            _ => Err(CogniaError::Internal(format!(
+                "Keychain lookup failed: {}",
+                output.stderr.trim()
+            ))),
+        }
+    }
+
+    pub async fn delete(account: &str) -> CogniaResult<bool> {
+        let output = process::execute(
+            "security",
+            &[
+                "delete-generic-password",
+                "-s",
+                KEYCHAIN_SERVICE,
+                "-a",
+                account,
+            ],
+            Some(options()),
+        )
+        .await?;
+        match output.exit_code {
+            0 => Ok(true),
+            ITEM_NOT_FOUND => Ok(false),
+            _ => Err(CogniaError::Internal(format!(
+                "Keychain delete failed: {}",
+                output.stderr.trim()
+            ))),
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+mod keychain {
+    use super::KEYCHAIN_SERVICE;
+    use crate::error::{CogniaError, CogniaResult};
+    use crate::platform::process::{self, ProcessOptions};
+    use std::time::Duration;
+
+    const TIMEOUT: Duration = Duration::from_secs(15);
+
+    fn options() -> ProcessOptions {
+        ProcessOptions::new().with_timeout(TIMEOUT)
+    }
+
+    /// `secret-tool` exits 1 with no output for a missing item, and prints
+    /// an error when the Secret Service itself is unreachable.
+    pub async fn available() -> bool {
+        process::execute(
+            "secret-tool",
+            &[
+                "lookup",
+                "service",
+                KEYCHAIN_SERVICE,
+                "account",
+                "availability-probe",
+            ],
+            Some(options()),
+        )
+        .await
+        .is_ok_and(|output| output.stderr.trim().is_empty())
+    }
+
+    pub async fn set(account: &str, value: &str) -> CogniaResult<()> {
+        let label = format!("--label={} {}", KEYCHAIN_SERVICE, account);
+        let output = process::execute(
+            "secret-tool",
+            &[
+                "store",
+                &label,
+                "service",
+                KEYCHAIN_SERVICE,
+                "account",
+                account,
+            ],
+            Some(options().with_stdin(value)),
+        )
+        .await?;
+        if !output.success {
+            return Err(CogniaError::Internal(format!(
+                "Secret Service rejected the secret: {}",
+                output.stderr.trim()
+            )));
+        }
+        Ok(())
+    }
+
+    pub async fn get(account: &str) -> CogniaResult<Option<String>> {
+        let output = process::execute(
+            "secret-tool",
+            &["lookup", "service", KEYCHAIN_SERVICE, "account", account],
+            Some(options()),
+        )
+        .await?;
+        if output.success {
+            return Ok(Some(output.stdout));
+        }
+        if output.stderr.trim().is_empty() {
+            return Ok(None);
+        }
+        Err(CogniaError::Internal(format!(
+            "Secret Service lookup failed: {}",
+            output.stderr.trim()
+        )))
+    }
+
+    pub async fn delete(account: &str) -> CogniaResult<bool> {
+        let output = process::execute(
+            "secret-tool",
+            &["clear", "service", KEYCHAIN_SERVICE, "account", account],
+            Some(options()),
+        )
+        .await?;
+        if !output.success && !output.stderr.trim().is_empty() {
+            return Err(CogniaError::Internal(format!(
+                "Secret Service delete failed: {}",
+                output.stderr.trim()
+            )));
+        }
+        Ok(output.success)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_encrypted_file_round_trip() {
+        let dir = tempdir().unwrap();
+        let store = SecretStore::encrypted_file(dir.path().to_path_buf());
+
+        let entry = store
+            .set("providers", "github.token", "ghp_abc")
+            .await
+            .unwrap();
+        assert_eq!(entry.backend, SecretBackend::EncryptedFile);
+        assert_eq!(
+            store
+                .get("providers", "github.token")
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("ghp_abc")
+        );
+
+        store
+            .set("providers", "github.token", "ghp_new")
+            .await
+            .unwrap();
+        assert_eq!(
+            store
+                .get("providers", "github.token")
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("ghp_new")
+        );
+        assert_eq!(store.get("providers", "gitlab.token").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_files_never_hold_plaintext() {
+        let dir = tempdir().unwrap();
+        let store = SecretStore::encrypted_file(dir.path().to_path_buf());
+        store
+            .set("providers", "github.token", "ghp_plaintext")
+            .await
+            .unwrap();
+
+        for file in [INDEX_FILE, ENCRYPTED_FILE] {
+            let content = std::fs::read_to_string(dir.path().join(file)).unwrap();
+            assert!(
+                !content.contains("ghp_plaintext"),
+                "{} leaks the value",
+                file
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_and_delete_by_namespace() {
+        let dir = tempdir().unwrap();
+        let store = SecretStore::encrypted_file(dir.path().to_path_buf());
+        store.set("providers", "gitlab.token", "a").await.unwrap();
+        store.set("providers", "github.token", "b").await.unwrap();
+        store.set("registries", "npm", "c").await.unwrap();
+
+        let keys: Vec<String> = store
+            .list("providers")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.key)
+            .collect();
+        assert_eq!(keys, vec!["github.token", "gitlab.token"]);
+
+        assert!(store.delete("providers", "github.token").await.unwrap());
+        assert!(!store.delete("providers", "github.token").await.unwrap());
+        assert!(!store.contains("providers", "github.token").await.unwrap());
+        assert_eq!(store.list("providers").await.unwrap().len(), 1);
+        assert_eq!(
+            store.get("registries", "npm").await.unwrap().as_deref(),
+            Some("c")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tampered_value_is_rejected() {
+        let dir = tempdir().unwrap();
+        let store = SecretStore::encrypted_file(dir.path().to_path_buf());
+        store
+            .set("providers", "github.token", "ghp_abc")
+            .await
+            .unwrap();
+
+        // A value copied onto another account fails the AAD check
+        let mut sealed = store.load_sealed().await.unwrap();
+        let value = sealed.entries["providers/github.token"].clone();
+        sealed
+            .entries
+            .insert("providers/gitlab.token".into(), value);
+        store.save_sealed(&sealed).await.unwrap();
+        let mut index = store.load_index().await.unwrap();
+        let mut entry = index.entries["providers/github.token"].clone();
+        entry.key = "gitlab.token".into();
+        index.entries.insert("providers/gitlab.token".into(), entry);
+        store.save_index(&index).await.unwrap();
+
+        assert!(store.get("providers", "gitlab.token").await.is_err());
+    }
+
+    #[test]
+    fn test_keychain_values_with_line_breaks_are_encoded() {
+        assert_eq!(encode_keychain_value("ghp_abc"), "ghp_abc");
+        for value in ["line one\nline two", "tab\there", "cognia-hex:literal"] {
+            let stored = encode_keychain_value(value);
+            assert!(stored.starts_with(HEX_VALUE_PREFIX));
+            assert!(!stored.contains('\n'));
+            assert_eq!(decode_keychain_value("a/b", &stored).unwrap(), value);
+        }
+        assert_eq!(decode_keychain_value("a/b", "ghp_abc").unwrap(), "ghp_abc");
+        assert!(decode_keychain_value("a/b", "cognia-hex:zz").is_err());
+    }
+
+    #[test]
+    fn test_names_are_validated() {
+        assert!(account_name("providers", "github.token").is_ok());
+        assert!(account_name("", "x").is_err());
+        assert!(account_name("providers", "a/b").is_err());
+        assert!(account_name("providers", "has space").is_err());
+    }
+}
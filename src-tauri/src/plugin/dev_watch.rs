@@ -0,0 +1,236 @@
+//! Development hot-reload for plugins.
+//!
+//! A watch polls the plugin's built wasm (and its source manifest) and reloads
+//! the plugin once the build output has settled, so partially written files
+//! from an in-progress `cargo build` are never loaded.
+
+use crate::plugin::manager::PluginManager;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::RwLock;
+
+/// How often watched files are checked
+pub const DEV_WATCH_POLL_INTERVAL: Duration = Duration::from_millis(250);
+/// Watched files must stay unchanged this long before a reload
+pub const DEV_WATCH_DEBOUNCE: Duration = Duration::from_millis(750);
+
+pub const PLUGIN_RELOADED_EVENT: &str = "plugin-reloaded";
+
+/// An active development watch
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginDevWatchInfo {
+    pub plugin_id: String,
+    pub wasm_path: String,
+    pub manifest_path: Option<String>,
+    pub started_at: String,
+}
+
+/// Payload of the `plugin-reloaded` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginReloadedEvent {
+    pub plugin_id: String,
+    pub wasm_path: String,
+    /// Modification time of the wasm that was loaded
+    pub build_timestamp: Option<String>,
+    pub reloaded_at: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// Running watch task; aborted when dropped
+pub(crate) struct DevWatcher {
+    pub info: PluginDevWatchInfo,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+impl DevWatcher {
+    pub fn new(info: PluginDevWatchInfo, task: tauri::async_runtime::JoinHandle<()>) -> Self {
+        Self { info, task }
+    }
+}
+
+impl Drop for DevWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileFingerprint {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+async fn fingerprint(path: &Path) -> Option<FileFingerprint> {
+    let meta = tokio::fs::metadata(path).await.ok()?;
+    Some(FileFingerprint {
+        modified: meta.modified().ok(),
+        len: meta.len(),
+    })
+}
+
+async fn snapshot(paths: &[PathBuf]) -> Vec<Option<FileFingerprint>> {
+    let mut fingerprints = Vec::with_capacity(paths.len());
+    for path in paths {
+        fingerprints.push(fingerprint(path).await);
+    }
+    fingerprints
+}
+
+/// Turns a stream of file snapshots into settled change notifications.
+/// The first snapshot must be the wasm, which has to exist for a reload.
+pub struct ChangeDebouncer {
+    debounce: Duration,
+    last_seen: Vec<Option<FileFingerprint>>,
+    applied: Vec<Option<FileFingerprint>>,
+    changed_at: Option<Instant>,
+}
+
+impl ChangeDebouncer {
+    pub fn new(initial: Vec<Option<FileFingerprint>>, debounce: Duration) -> Self {
+        Self {
+            debounce,
+            last_seen: initial.clone(),
+            applied: initial,
+            changed_at: None,
+        }
+    }
+
+    /// Record a snapshot; returns true once a change has settled and should be applied
+    pub fn observe(&mut self, current: Vec<Option<FileFingerprint>>, now: Instant) -> bool {
+        if current != self.last_seen {
+            self.last_seen = current;
+            self.changed_at = Some(now);
+            return false;
+        }
+
+        let settled = self
+            .changed_at
+            .is_some_and(|at| now.duration_since(at) >= self.debounce);
+        let wasm_present = self.last_seen.first().is_some_and(|f| f.is_some());
+        if settled && wasm_present && self.last_seen != self.applied {
+            self.applied = self.last_seen.clone();
+            self.changed_at = None;
+            return true;
+        }
+        false
+    }
+}
+
+/// Spawn the polling task for a watch. Reloads go through the shared manager
+/// and every attempt is reported with a `plugin-reloaded` event.
+pub(crate) fn spawn_dev_watch(
+    manager: Arc<RwLock<PluginManager>>,
+    app: AppHandle,
+    info: PluginDevWatchInfo,
+) -> tauri::async_runtime::JoinHandle<()> {
+    tauri::async_runtime::spawn(async move {
+        let wasm_path = PathBuf::from(&info.wasm_path);
+        let manifest_path = info.manifest_path.as_ref().map(PathBuf::from);
+        let mut paths = vec![wasm_path.clone()];
+        paths.extend(manifest_path.clone());
+
+        let mut debouncer = ChangeDebouncer::new(snapshot(&paths).await, DEV_WATCH_DEBOUNCE);
+        loop {
+            tokio::time::sleep(DEV_WATCH_POLL_INTERVAL).await;
+            if !debouncer.observe(snapshot(&paths).await, Instant::now()) {
+                continue;
+            }
+
+            let result = {
+                let mut mgr = manager.write().await;
+                mgr.reload_dev_build(&info.plugin_id, &wasm_path, manifest_path.as_deref())
+                    .await
+            };
+            let build_timestamp = fingerprint(&wasm_path)
+                .await
+                .and_then(|f| f.modified)
+                .map(|t| DateTime::<Utc>::from(t).to_rfc3339());
+
+            match &result {
+                Ok(()) => log::info!("Hot-reloaded dev plugin '{}'", info.plugin_id),
+                Err(e) => log::warn!(
+                    "Hot-reload of dev plugin '{}' failed: {}",
+                    info.plugin_id,
+                    e
+                ),
+            }
+
+            let event = PluginReloadedEvent {
+                plugin_id: info.plugin_id.clone(),
+                wasm_path: info.wasm_path.clone(),
+                build_timestamp,
+                reloaded_at: Utc::now().to_rfc3339(),
+                success: result.is_ok(),
+                error: result.err().map(|e| e.to_string()),
+            };
+            if let Err(e) = app.emit(PLUGIN_RELOADED_EVENT, &event) {
+                log::warn!("Failed to emit {}: {}", PLUGIN_RELOADED_EVENT, e);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fp(len: u64) -> Option<FileFingerprint> {
+        Some(FileFingerprint {
+            modified: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(len)),
+            len,
+        })
+    }
+
+    #[test]
+    fn test_debouncer_waits_for_settled_change() {
+        let debounce = Duration::from_millis(500);
+        let start = Instant::now();
+        let mut debouncer = ChangeDebouncer::new(vec![fp(1), fp(10)], debounce);
+
+        // Nothing changed
+        assert!(!debouncer.observe(vec![fp(1), fp(10)], start));
+
+        // Build in progress: file keeps growing
+        assert!(!debouncer.observe(vec![fp(2), fp(10)], start));
+        assert!(!debouncer.observe(vec![fp(3), fp(10)], start + Duration::from_millis(200)));
+        assert!(!debouncer.observe(vec![fp(3), fp(10)], start + Duration::from_millis(400)));
+
+        // Stable for the debounce window
+        assert!(debouncer.observe(vec![fp(3), fp(10)], start + Duration::from_millis(800)));
+        // Applied only once
+        assert!(!debouncer.observe(vec![fp(3), fp(10)], start + Duration::from_millis(1600)));
+    }
+
+    #[test]
+    fn test_debouncer_ignores_missing_wasm() {
+        let debounce = Duration::from_millis(100);
+        let start = Instant::now();
+        let mut debouncer = ChangeDebouncer::new(vec![fp(1)], debounce);
+
+        // wasm removed by `cargo clean` / mid-build
+        assert!(!debouncer.observe(vec![None], start));
+        assert!(!debouncer.observe(vec![None], start + Duration::from_millis(500)));
+
+        // Rebuilt
+        assert!(!debouncer.observe(vec![fp(5)], start + Duration::from_millis(600)));
+        assert!(debouncer.observe(vec![fp(5)], start + Duration::from_millis(800)));
+    }
+
+    #[test]
+    fn test_debouncer_reverted_change_is_not_applied() {
+        let debounce = Duration::from_millis(100);
+        let start = Instant::now();
+        let mut debouncer = ChangeDebouncer::new(vec![fp(1)], debounce);
+
+        assert!(!debouncer.observe(vec![fp(2)], start));
+        assert!(!debouncer.observe(vec![fp(1)], start + Duration::from_millis(50)));
+        assert!(!debouncer.observe(vec![fp(1)], start + Duration::from_millis(500)));
+    }
+}
@@ -3,10 +3,12 @@
 use crate::core::profiles::{EnvironmentProfile, ProfileManager};
 use crate::download::DownloadManager;
 use crate::platform::process;
+use crate::plugin::i18n;
 use crate::plugin::permissions::PermissionManager;
 use crate::plugin::registry::PluginRegistry as CogniaPluginRegistry;
+use crate::plugin::storage::{quota_bytes_from_mb, PluginStore, StorageError};
 use crate::provider::registry::ProviderRegistry;
-use crate::provider::traits::Provider;
+use crate::provider::traits::{InstallProgressEvent, InstallReceipt, InstallStage, Provider};
 use extism::{host_fn, Error as ExtismError, UserData, ValType};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -57,6 +59,40 @@ pub struct EmittedPluginUiEffect {
     pub payload: serde_json::Value,
 }
 
+/// Event delivered to the owning plugin whenever a package operation it
+/// started makes progress or finishes.
+pub const PKG_OPERATION_EVENT: &str = "pkg_install_progress";
+
+/// Finished package operations kept for status queries.
+const PKG_OPERATION_HISTORY_LIMIT: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginPkgOperationStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+/// A package install started by a plugin through `cognia_pkg_install_start`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginPkgOperation {
+    pub id: String,
+    pub plugin_id: String,
+    pub provider: String,
+    pub name: String,
+    pub version: Option<String>,
+    pub status: PluginPkgOperationStatus,
+    pub stage: Option<InstallStage>,
+    pub progress: f32,
+    pub message: Option<String>,
+    pub error: Option<String>,
+    pub receipt: Option<InstallReceipt>,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+}
+
 /// Shared context passed to host functions via Extism UserData.
 /// This gives WASM plugins controlled access to the launcher's core APIs.
 #[derive(Clone)]
@@ -85,6 +121,8 @@ pub struct HostContext {
     pub download_manager: Option<Arc<RwLock<DownloadManager>>>,
     /// Profile manager for profile host functions.
     pub profile_manager: Option<Arc<RwLock<ProfileManager>>>,
+    /// Package operations started by plugins, keyed by operation id.
+    pub pkg_operations: Arc<RwLock<HashMap<String, PluginPkgOperation>>>,
 }
 
 impl HostContext {
@@ -109,6 +147,7 @@ pub fn new(
             app_handle: Arc::new(RwLock::new(app_handle)),
             download_manager: None,
             profile_manager: None,
+            pkg_operations: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -178,6 +217,42 @@ pub async fn set_log_dispatch_active(&self, active: bool) {
     pub async fn app_handle(&self) -> Option<tauri::AppHandle> {
         self.app_handle.read().await.clone()
     }
+
+    /// Look up a package operation, visible only to the plugin that started it
+    pub async fn pkg_operation(&self, plugin_id: &str, id: &str) -> Option<PluginPkgOperation> {
+        let operations = self.pkg_operations.read().await;
+        operations
+            .get(id)
+            .filter(|operation| operation.plugin_id == plugin_id)
+            .cloned()
+    }
+
+    async fn insert_pkg_operation(&self, operation: PluginPkgOperation) {
+        let mut operations = self.pkg_operations.write().await;
+        let mut finished: Vec<(String, String)> = operations
+            .values()
+            .filter_map(|op| op.finished_at.clone().map(|at| (at, op.id.clone())))
+            .collect();
+        if finished.len() >= PKG_OPERATION_HISTORY_LIMIT {
+            finished.sort();
+            let excess = finished.len() + 1 - PKG_OPERATION_HISTORY_LIMIT;
+            for (_, id) in finished.into_iter().take(excess) {
+                operations.remove(&id);
+            }
+        }
+        operations.insert(operation.id.clone(), operation);
+    }
+
+    async fn update_pkg_operation(
+        &self,
+        id: &str,
+        update: impl FnOnce(&mut PluginPkgOperation),
+    ) -> Option<PluginPkgOperation> {
+        let mut operations = self.pkg_operations.write().await;
+        let operation = operations.get_mut(id)?;
+        update(operation);
+        Some(operation.clone())
+    }
 }
 
 enum CapturedRuntime {
@@ -728,6 +803,43 @@ fn serialize_process_availability(available: bool) -> Result<String, ExtismError
         .map_err(|e| ExtismError::msg(format!("Failed to serialize process availability: {}", e)))
 }
 
+/// Load the settings schema and stored values for a plugin's own settings.
+async fn load_plugin_settings(
+    ctx: &HostContext,
+    plugin_id: &str,
+) -> Result<
+    (
+        Vec<crate::plugin::manifest::SettingDeclaration>,
+        HashMap<String, serde_json::Value>,
+    ),
+    ExtismError,
+> {
+    let schema = ctx
+        .plugin_registry
+        .read()
+        .await
+        .get(plugin_id)
+        .map(|plugin| plugin.manifest.settings.clone())
+        .unwrap_or_default();
+    let data_dir = ctx.permissions.read().await.get_plugin_data_dir(plugin_id);
+    let stored = crate::plugin::manager::read_plugin_settings_file(&data_dir)
+        .await
+        .map_err(|e| ExtismError::msg(e.to_string()))?;
+    Ok((schema, stored))
+}
+
+/// Open the calling plugin's key-value store with the configured quota.
+async fn open_plugin_store(ctx: &HostContext, plugin_id: &str) -> PluginStore {
+    let data_dir = ctx.permissions.read().await.get_plugin_data_dir(plugin_id);
+    let quota_mb = ctx.settings.read().await.plugin.storage_quota_mb;
+    PluginStore::new(&data_dir, quota_bytes_from_mb(quota_mb))
+}
+
+#[track_caller]
+fn map_storage_error(plugin_id: &str, error: StorageError) -> ExtismError {
+    log_boundary_error(Some(plugin_id), "storage", error.to_string())
+}
+
 fn serialize_json<T: Serialize>(value: &T) -> Result<String, ExtismError> {
     serde_json::to_string(value)
         .map_err(|error| ExtismError::msg(format!("Failed to serialize host result: {}", error)))
@@ -756,6 +868,165 @@ async fn ensure_process_exec_permission(ctx: &HostContext) -> Result<(), ExtismE
     Ok(())
 }
 
+async fn ensure_pkg_install_permission(ctx: &HostContext) -> Result<String, ExtismError> {
+    let plugin_id = require_current_plugin_id(ctx).await?;
+    let perms = ctx.permissions.read().await;
+    check_permission(&perms, &plugin_id, "pkg_install")?;
+    drop(perms);
+    Ok(plugin_id)
+}
+
+/// Resolve a provider named by a plugin, rejecting unknown and disabled ones.
+#[track_caller]
+fn resolve_plugin_pkg_provider(
+    registry: &ProviderRegistry,
+    plugin_id: &str,
+    provider_id: &str,
+) -> Result<Arc<dyn Provider>, ExtismError> {
+    let Some(provider) = registry.get(provider_id) else {
+        let mut enabled = registry.list();
+        enabled.sort_unstable();
+        return Err(log_boundary_error(
+            Some(plugin_id),
+            "provider",
+            format!(
+                "Unknown provider '{}' (enabled providers: {})",
+                provider_id,
+                enabled.join(", ")
+            ),
+        ));
+    };
+    if !registry.is_provider_enabled(provider_id) {
+        return Err(log_boundary_error(
+            Some(plugin_id),
+            "provider",
+            format!("Provider '{}' is disabled", provider_id),
+        ));
+    }
+    Ok(provider)
+}
+
+fn apply_pkg_progress(operation: &mut PluginPkgOperation, event: &InstallProgressEvent) {
+    operation.stage = Some(event.stage);
+    operation.progress = event.progress_percent.clamp(0.0, 100.0);
+    if !event.message.is_empty() {
+        operation.message = Some(event.message.clone());
+    }
+}
+
+fn finish_pkg_operation(
+    operation: &mut PluginPkgOperation,
+    result: Result<InstallReceipt, String>,
+    finished_at: String,
+) {
+    match result {
+        Ok(receipt) => {
+            operation.status = PluginPkgOperationStatus::Completed;
+            operation.stage = Some(InstallStage::Done);
+            operation.progress = 100.0;
+            operation.receipt = Some(receipt);
+        }
+        Err(error) => {
+            operation.status = PluginPkgOperationStatus::Failed;
+            operation.stage = Some(InstallStage::Failed);
+            operation.error = Some(error);
+        }
+    }
+    operation.finished_at = Some(finished_at);
+}
+
+/// Forward a package operation update to the plugin that started it.
+async fn notify_pkg_operation(ctx: &HostContext, operation: &PluginPkgOperation) {
+    use tauri::Manager;
+
+    let Some(app) = ctx.app_handle().await else {
+        return;
+    };
+    let Some(manager) = app.try_state::<crate::commands::plugin::SharedPluginManager>() else {
+        return;
+    };
+    let Ok(payload) = serde_json::to_value(operation) else {
+        return;
+    };
+    let manager = manager.inner().clone();
+    let mut manager = manager.write().await;
+    manager
+        .dispatch_event_to_plugin(&operation.plugin_id, PKG_OPERATION_EVENT, &payload)
+        .await;
+}
+
+/// Drive a plugin-started install to completion, mirroring provider progress
+/// into the operation record and recording the plugin as the actor in history.
+async fn run_plugin_pkg_install(
+    ctx: HostContext,
+    provider: Arc<dyn Provider>,
+    operation_id: String,
+    plugin_id: String,
+    request: crate::provider::InstallRequest,
+) {
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<InstallProgressEvent>(32);
+    let forward_ctx = ctx.clone();
+    let forward_id = operation_id.clone();
+    let forwarder = tokio::spawn(async move {
+        while let Some(event) = rx.recv().await {
+            let updated = forward_ctx
+                .update_pkg_operation(&forward_id, |operation| {
+                    apply_pkg_progress(operation, &event)
+                })
+                .await;
+            if let Some(operation) = updated {
+                notify_pkg_operation(&forward_ctx, &operation).await;
+            }
+        }
+    });
+
+    let result = provider
+        .install_with_progress(request.clone(), Some(tx))
+        .await
+        .map_err(|e| e.to_string());
+    let _ = forwarder.await;
+
+    let (version, error) = match &result {
+        Ok(receipt) => (receipt.version.clone(), None),
+        Err(e) => (
+            request
+                .version
+                .clone()
+                .unwrap_or_else(|| "latest".to_string()),
+            Some(e.clone()),
+        ),
+    };
+    let _ = crate::core::HistoryManager::record_install_by(
+        &request.name,
+        &version,
+        provider.id(),
+        result.is_ok(),
+        error,
+        Some(&plugin_id),
+    )
+    .await;
+    if result.is_ok() {
+        crate::commands::package::invalidate_package_caches(&ctx.settings).await;
+    }
+
+    let finished_at = chrono::Utc::now().to_rfc3339();
+    let updated = ctx
+        .update_pkg_operation(&operation_id, |operation| {
+            finish_pkg_operation(operation, result, finished_at)
+        })
+        .await;
+    if let Some(operation) = updated {
+        log::info!(
+            "[plugin:{}] pkg operation {} for {} finished: {:?}",
+            plugin_id,
+            operation_id,
+            request.name,
+            operation.status
+        );
+        notify_pkg_operation(&ctx, &operation).await;
+    }
+}
+
 // ============================================================================
 // Host Function Implementations
 // ============================================================================
@@ -820,6 +1091,109 @@ async fn ensure_process_exec_permission(ctx: &HostContext) -> Result<(), ExtismE
     Ok(r#"{"ok":true}"#.to_string())
 });
 
+// Read the calling plugin's own setting, resolved against its declared schema.
+// Input: JSON { "key": "setting-id" }
+// Output: JSON { "key": "...", "value": <json|null>, "source": "stored"|"default"|"unset" }
+host_fn!(pub cognia_config_get_typed(user_data: HostContext; input: String) -> String {
+    let ctx = user_data.get()?;
+    let ctx = ctx
+        .lock()
+        .map_err(|_| log_boundary_error(None, "context", "failed to acquire host context lock"))?
+        .clone();
+
+    #[derive(Deserialize)]
+    struct Input { key: String }
+
+    let req: Input = serde_json::from_str(&input)
+        .map_err(|e| ExtismError::msg(format!("Invalid input: {}", e)))?;
+
+    let rt = HostRuntimeBridge::capture()?;
+
+    rt.block_on(async {
+        let plugin_id = require_current_plugin_id(&ctx).await?;
+        let (schema, stored) = load_plugin_settings(&ctx, &plugin_id).await?;
+        let resolved = crate::plugin::manifest::resolve_setting_values(&schema, &stored);
+
+        let (value, source) = if let Some(value) = resolved.values.get(&req.key) {
+            let source = if resolved.defaulted_keys.contains(&req.key) { "default" } else { "stored" };
+            (value.clone(), source)
+        } else if schema.is_empty() {
+            match stored.get(&req.key) {
+                Some(value) => (value.clone(), "stored"),
+                None => (serde_json::Value::Null, "unset"),
+            }
+        } else {
+            (serde_json::Value::Null, "unset")
+        };
+
+        serialize_json(&serde_json::json!({
+            "key": req.key,
+            "value": value,
+            "source": source,
+        }))
+    })
+});
+
+// Write the calling plugin's own setting. Values are validated against the
+// declared schema; invalid values are rejected with the expected type.
+// Input: JSON { "key": "setting-id", "value": <json> }
+// Output: JSON { "ok": true, "value": <normalized json> }
+host_fn!(pub cognia_config_set_typed(user_data: HostContext; input: String) -> String {
+    let ctx = user_data.get()?;
+    let ctx = ctx
+        .lock()
+        .map_err(|_| log_boundary_error(None, "context", "failed to acquire host context lock"))?
+        .clone();
+
+    #[derive(Deserialize)]
+    struct Input { key: String, value: serde_json::Value }
+
+    let req: Input = serde_json::from_str(&input)
+        .map_err(|e| ExtismError::msg(format!("Invalid input: {}", e)))?;
+
+    let rt = HostRuntimeBridge::capture()?;
+
+    rt.block_on(async {
+        let plugin_id = require_current_plugin_id(&ctx).await?;
+        let (schema, _) = load_plugin_settings(&ctx, &plugin_id).await?;
+        let value = crate::plugin::manager::validate_plugin_setting(&schema, &req.key, &req.value)
+            .map_err(|e| ExtismError::msg(e.to_string()))?;
+
+        let data_dir = ctx.permissions.read().await.get_plugin_data_dir(&plugin_id);
+        crate::plugin::manager::write_plugin_setting(&data_dir, &req.key, value.clone())
+            .await
+            .map_err(|e| ExtismError::msg(e.to_string()))?;
+
+        serialize_json(&serde_json::json!({ "ok": true, "value": value }))
+    })
+});
+
+// Read all of the calling plugin's settings: declared defaults merged with stored values.
+// Input: (empty string)
+// Output: JSON ResolvedPluginSettings { values, defaultedKeys, unknownKeys, invalidKeys, unknownValues }
+host_fn!(pub cognia_config_all(user_data: HostContext; _input: String) -> String {
+    let ctx = user_data.get()?;
+    let ctx = ctx
+        .lock()
+        .map_err(|_| log_boundary_error(None, "context", "failed to acquire host context lock"))?
+        .clone();
+
+    let rt = HostRuntimeBridge::capture()?;
+
+    rt.block_on(async {
+        let plugin_id = require_current_plugin_id(&ctx).await?;
+        let (schema, stored) = load_plugin_settings(&ctx, &plugin_id).await?;
+        let mut resolved = crate::plugin::manifest::resolve_setting_values(&schema, &stored);
+        if schema.is_empty() {
+            // Schema-less plugins: every stored key is a plain value, not drift.
+            resolved.values = stored;
+            resolved.unknown_keys.clear();
+            resolved.unknown_values.clear();
+        }
+        serialize_json(&resolved)
+    })
+});
+
 // --- Environment ---
 
 // List all environments. Requires: env_read permission.
@@ -920,12 +1294,9 @@ struct SearchInput {
         let options = crate::provider::SearchOptions { limit: Some(20), page: None };
 
         let results = if let Some(provider_id) = &search.provider {
-            if let Some(provider) = registry.get(provider_id) {
-                provider.search(&search.query, options).await
-                    .unwrap_or_default()
-            } else {
-                vec![]
-            }
+            let provider = resolve_plugin_pkg_provider(&registry, &plugin_id, provider_id)?;
+            provider.search(&search.query, options).await
+                .unwrap_or_default()
         } else {
             // Search across all available providers (first match)
             let mut found = vec![];
@@ -1023,6 +1394,146 @@ struct FsWriteInput { path: String, content: String }
     Ok(r#"{"ok":true}"#.to_string())
 });
 
+// --- Storage (per-plugin key-value store) ---
+
+// Read a value from the calling plugin's store.
+// Input: JSON { "key": "..." }
+// Output: JSON { "value": "<hex bytes>" } or { "value": null }
+host_fn!(pub cognia_storage_get(user_data: HostContext; input: String) -> String {
+    let ctx = user_data.get()?;
+    let ctx = ctx
+        .lock()
+        .map_err(|_| log_boundary_error(None, "context", "failed to acquire host context lock"))?
+        .clone();
+
+    #[derive(Deserialize)]
+    struct Input { key: String }
+
+    let req: Input = serde_json::from_str(&input)
+        .map_err(|e| ExtismError::msg(format!("Invalid input: {}", e)))?;
+
+    let rt = HostRuntimeBridge::capture()?;
+
+    rt.block_on(async {
+        let plugin_id = require_current_plugin_id(&ctx).await?;
+        let store = open_plugin_store(&ctx, &plugin_id).await;
+        let value = store
+            .get(&req.key)
+            .await
+            .map_err(|e| map_storage_error(&plugin_id, e))?;
+        serialize_json(&serde_json::json!({ "value": value.map(hex::encode) }))
+    })
+});
+
+// Write a value into the calling plugin's store, enforcing the storage quota.
+// Input: JSON { "key": "...", "value": "<hex bytes>" }
+// Output: JSON { "ok": true } or, when the quota would be exceeded,
+// { "ok": false, "error": { "code": "quota_exceeded", "message", "usageBytes", "quotaBytes", "requiredBytes" } }
+host_fn!(pub cognia_storage_set(user_data: HostContext; input: String) -> String {
+    let ctx = user_data.get()?;
+    let ctx = ctx
+        .lock()
+        .map_err(|_| log_boundary_error(None, "context", "failed to acquire host context lock"))?
+        .clone();
+
+    #[derive(Deserialize)]
+    struct Input { key: String, value: String }
+
+    let req: Input = serde_json::from_str(&input)
+        .map_err(|e| ExtismError::msg(format!("Invalid input: {}", e)))?;
+    let value = hex::decode(&req.value)
+        .map_err(|e| ExtismError::msg(format!("Invalid input: value is not hex: {}", e)))?;
+
+    let rt = HostRuntimeBridge::capture()?;
+
+    rt.block_on(async {
+        let plugin_id = require_current_plugin_id(&ctx).await?;
+        let store = open_plugin_store(&ctx, &plugin_id).await;
+        match store.set(&req.key, &value).await {
+            Ok(()) => serialize_json(&serde_json::json!({ "ok": true })),
+            Err(error @ StorageError::QuotaExceeded { usage_bytes, quota_bytes, required_bytes }) => {
+                log::warn!("[plugin-runtime][plugin:{}] {}", plugin_id, error);
+                serialize_json(&serde_json::json!({
+                    "ok": false,
+                    "error": {
+                        "code": "quota_exceeded",
+                        "message": error.to_string(),
+                        "usageBytes": usage_bytes,
+                        "quotaBytes": quota_bytes,
+                        "requiredBytes": required_bytes,
+                    },
+                }))
+            }
+            Err(error) => Err(map_storage_error(&plugin_id, error)),
+        }
+    })
+});
+
+// Delete a key from the calling plugin's store.
+// Input: JSON { "key": "..." }
+// Output: JSON { "deleted": bool }
+host_fn!(pub cognia_storage_delete(user_data: HostContext; input: String) -> String {
+    let ctx = user_data.get()?;
+    let ctx = ctx
+        .lock()
+        .map_err(|_| log_boundary_error(None, "context", "failed to acquire host context lock"))?
+        .clone();
+
+    #[derive(Deserialize)]
+    struct Input { key: String }
+
+    let req: Input = serde_json::from_str(&input)
+        .map_err(|e| ExtismError::msg(format!("Invalid input: {}", e)))?;
+
+    let rt = HostRuntimeBridge::capture()?;
+
+    rt.block_on(async {
+        let plugin_id = require_current_plugin_id(&ctx).await?;
+        let store = open_plugin_store(&ctx, &plugin_id).await;
+        let deleted = store
+            .delete(&req.key)
+            .await
+            .map_err(|e| map_storage_error(&plugin_id, e))?;
+        serialize_json(&serde_json::json!({ "deleted": deleted }))
+    })
+});
+
+// List keys in the calling plugin's store that start with a prefix.
+// Input: JSON { "prefix": "..." } (prefix optional)
+// Output: JSON array of { key, sizeBytes, modifiedAt }
+host_fn!(pub cognia_storage_list(user_data: HostContext; input: String) -> String {
+    let ctx = user_data.get()?;
+    let ctx = ctx
+        .lock()
+        .map_err(|_| log_boundary_error(None, "context", "failed to acquire host context lock"))?
+        .clone();
+
+    #[derive(Deserialize, Default)]
+    struct Input {
+        #[serde(default)]
+        prefix: String,
+    }
+
+    let req: Input = if input.trim().is_empty() {
+        Input::default()
+    } else {
+        serde_json::from_str(&input)
+            .map_err(|e| ExtismError::msg(format!("Invalid input: {}", e)))?
+    };
+
+    let rt = HostRuntimeBridge::capture()?;
+
+    rt.block_on(async {
+        let plugin_id = require_current_plugin_id(&ctx).await?;
+        let store = open_plugin_store(&ctx, &plugin_id).await;
+        let entries = store
+            .list(&req.prefix)
+            .await
+            .map_err(|e| map_storage_error(&plugin_id, e))?;
+        serialize_json(&entries)
+    })
+});
+
 // --- HTTP (restricted to declared domains) ---
 
 // Make an HTTP GET request. Requires: http permission + URL in allowed domains.
@@ -1280,29 +1791,143 @@ struct InstallInput { name: String, version: Option<String>, provider: Option<St
     let rt = HostRuntimeBridge::capture()?;
 
     let result = rt.block_on(async {
-        let plugin_id = require_current_plugin_id(&ctx).await?;
-        let perms = ctx.permissions.read().await;
-        check_permission(&perms, &plugin_id, "pkg_install")?;
-        drop(perms);
+        let plugin_id = ensure_pkg_install_permission(&ctx).await?;
 
-        let registry = ctx.registry.read().await;
+        let Some(provider_id) = &install.provider else {
+            return Err(ExtismError::msg(format!("No provider found for package '{}'", install.name)));
+        };
+        let provider = {
+            let registry = ctx.registry.read().await;
+            resolve_plugin_pkg_provider(&registry, &plugin_id, provider_id)?
+        };
         let request = crate::provider::InstallRequest {
             name: install.name.clone(),
-            version: install.version,
+            version: install.version.clone(),
             force: false,
             global: true,
         };
 
-        if let Some(provider_id) = &install.provider {
-            if let Some(provider) = registry.get(provider_id) {
-                let receipt = provider.install(request).await
-                    .map_err(|e| ExtismError::msg(e.to_string()))?;
-                return Ok(serde_json::to_string(&receipt)
-                    .map_err(|e| ExtismError::msg(e.to_string()))?);
-            }
-        }
+        let outcome = provider.install(request).await;
+        let (version, error) = match &outcome {
+            Ok(receipt) => (receipt.version.clone(), None),
+            Err(e) => (
+                install.version.clone().unwrap_or_else(|| "latest".to_string()),
+                Some(e.to_string()),
+            ),
+        };
+        let _ = crate::core::HistoryManager::record_install_by(
+            &install.name,
+            &version,
+            provider.id(),
+            outcome.is_ok(),
+            error,
+            Some(&plugin_id),
+        )
+        .await;
+
+        let receipt = outcome.map_err(|e| ExtismError::msg(e.to_string()))?;
+        crate::commands::package::invalidate_package_caches(&ctx.settings).await;
+        serde_json::to_string(&receipt).map_err(|e| ExtismError::msg(e.to_string()))
+    })?;
+
+    Ok(result)
+});
+
+// Start a package install in the background. Requires: pkg_install permission (dangerous).
+// Progress and completion are delivered to the calling plugin as `pkg_install_progress` events.
+// Input: JSON { "provider": "npm", "name": "express", "version": null }
+// Output: JSON { "operationId": "..." }
+host_fn!(pub cognia_pkg_install_start(user_data: HostContext; input: String) -> String {
+    let ctx = user_data.get()?;
+    let ctx = ctx
+        .lock()
+        .map_err(|_| log_boundary_error(None, "context", "failed to acquire host context lock"))?
+        .clone();
+
+    #[derive(Deserialize)]
+    struct InstallStartInput { provider: String, name: String, version: Option<String> }
+
+    let req: InstallStartInput = serde_json::from_str(&input)
+        .map_err(|e| ExtismError::msg(format!("Invalid input: {}", e)))?;
+
+    let rt = HostRuntimeBridge::capture()?;
+
+    let result = rt.block_on(async {
+        let plugin_id = ensure_pkg_install_permission(&ctx).await?;
+        let provider = {
+            let registry = ctx.registry.read().await;
+            resolve_plugin_pkg_provider(&registry, &plugin_id, &req.provider)?
+        };
+
+        let operation_id = uuid::Uuid::new_v4().to_string();
+        ctx.insert_pkg_operation(PluginPkgOperation {
+            id: operation_id.clone(),
+            plugin_id: plugin_id.clone(),
+            provider: req.provider.clone(),
+            name: req.name.clone(),
+            version: req.version.clone(),
+            status: PluginPkgOperationStatus::Running,
+            stage: None,
+            progress: 0.0,
+            message: None,
+            error: None,
+            receipt: None,
+            started_at: chrono::Utc::now().to_rfc3339(),
+            finished_at: None,
+        })
+        .await;
+
+        let request = crate::provider::InstallRequest {
+            name: req.name.clone(),
+            version: req.version.clone(),
+            force: false,
+            global: true,
+        };
+        tauri::async_runtime::spawn(run_plugin_pkg_install(
+            ctx.clone(),
+            provider,
+            operation_id.clone(),
+            plugin_id.clone(),
+            request,
+        ));
+        log::info!(
+            "[plugin:{}] pkg_install_start: {} via {} (operation {})",
+            plugin_id,
+            req.name,
+            req.provider,
+            operation_id
+        );
 
-        Err(ExtismError::msg(format!("No provider found for package '{}'", install.name)))
+        Ok::<_, ExtismError>(serde_json::json!({ "operationId": operation_id }).to_string())
+    })?;
+
+    Ok(result)
+});
+
+// Get the status of a package operation started by the calling plugin.
+// Input: JSON { "id": "..." }
+// Output: JSON PluginPkgOperation
+host_fn!(pub cognia_pkg_operation_status(user_data: HostContext; input: String) -> String {
+    let ctx = user_data.get()?;
+    let ctx = ctx
+        .lock()
+        .map_err(|_| log_boundary_error(None, "context", "failed to acquire host context lock"))?
+        .clone();
+
+    #[derive(Deserialize)]
+    struct StatusInput { id: String }
+
+    let req: StatusInput = serde_json::from_str(&input)
+        .map_err(|e| ExtismError::msg(format!("Invalid input: {}", e)))?;
+
+    let rt = HostRuntimeBridge::capture()?;
+
+    let result = rt.block_on(async {
+        let plugin_id = require_current_plugin_id(&ctx).await?;
+        let operation = ctx.pkg_operation(&plugin_id, &req.id).await.ok_or_else(|| {
+            ExtismError::msg(format!("Unknown package operation '{}'", req.id))
+        })?;
+        serde_json::to_string(&operation).map_err(|e| ExtismError::msg(e.to_string()))
     })?;
 
     Ok(result)
@@ -2568,7 +3193,7 @@ struct FsInput { path: String }
 // Translate a key using the plugin's locale data. Always allowed.
 // Input: JSON { "key": "greeting", "params": { "name": "World" } }
 // Output: JSON { "text": "Hello, World!" }
-// Falls back to: current locale -> "en" -> raw key
+// Falls back to: current locale -> language only -> plugin default locale -> raw key
 host_fn!(pub cognia_i18n_translate(user_data: HostContext; input: String) -> String {
     let ctx = user_data.get()?;
     let ctx = ctx
@@ -2596,20 +3221,27 @@ struct TranslateInput {
         let locale = settings.get_value("language").unwrap_or_else(|| "en".to_string());
         drop(settings);
 
-        // Look up the key in plugin's locale data
+        // Look up the key along the plugin's locale fallback chain
         let plugin_reg = ctx.plugin_registry.read().await;
-        let text = if let Some(plugin) = plugin_reg.get(&plugin_id) {
-            let locales = &plugin.manifest.locales;
-            // Try current locale, then fallback to "en", then raw key
-            locales.get(&locale)
-                .and_then(|m| m.get(&req.key))
-                .or_else(|| locales.get("en").and_then(|m| m.get(&req.key)))
-                .cloned()
-                .unwrap_or_else(|| req.key.clone())
-        } else {
-            req.key.clone()
-        };
+        let found = plugin_reg.get(&plugin_id).and_then(|plugin| {
+            i18n::lookup(
+                &plugin.manifest.locales,
+                &locale,
+                plugin.manifest.plugin.default_locale.as_deref(),
+                &req.key,
+            )
+            .map(str::to_string)
+        });
         drop(plugin_reg);
+        let text = found.unwrap_or_else(|| {
+            log::warn!(
+                "[plugin-runtime][plugin:{}][operation:i18n_translate] missing translation for key '{}' (locale {})",
+                plugin_id,
+                req.key,
+                locale
+            );
+            req.key.clone()
+        });
 
         // Interpolate parameters: replace {param} with value
         let mut result = text;
@@ -2624,6 +3256,7 @@ struct TranslateInput {
 });
 
 // Get all locale strings for the plugin's current locale. Always allowed.
+// Strings missing from the resolved locale are filled in along the fallback chain.
 // Input: (empty string)
 // Output: JSON { "locale": "en", "strings": { "key": "value", ... } }
 host_fn!(pub cognia_i18n_get_all(user_data: HostContext; _input: String) -> String {
@@ -2643,20 +3276,20 @@ struct TranslateInput {
         drop(settings);
 
         let plugin_reg = ctx.plugin_registry.read().await;
-        let strings = if let Some(plugin) = plugin_reg.get(&plugin_id) {
+        let (resolved, strings) = if let Some(plugin) = plugin_reg.get(&plugin_id) {
             let locales = &plugin.manifest.locales;
-            // Try current locale, fallback to "en", then empty
-            locales.get(&locale)
-                .or_else(|| locales.get("en"))
-                .cloned()
-                .unwrap_or_default()
+            let default_locale = plugin.manifest.plugin.default_locale.as_deref();
+            (
+                i18n::resolve_locale(locales, &locale, default_locale).map(str::to_string),
+                i18n::merged_strings(locales, &locale, default_locale),
+            )
         } else {
-            HashMap::new()
+            (None, HashMap::new())
         };
         drop(plugin_reg);
 
         Ok::<_, ExtismError>(serde_json::json!({
-            "locale": locale,
+            "locale": resolved.unwrap_or(locale),
             "strings": strings,
         }).to_string())
     })?;
@@ -2664,6 +3297,58 @@ struct TranslateInput {
     Ok(result)
 });
 
+// Get the plugin's locale resolution state. Always allowed.
+// Input: (empty string)
+// Output: JSON { "locale": "zh-TW", "resolved": "zh", "available": ["en", "zh"], "defaultLocale": "en" }
+// `resolved` is null when the plugin ships no matching locale.
+host_fn!(pub cognia_i18n_get_locales(user_data: HostContext; _input: String) -> String {
+    let ctx = user_data.get()?;
+    let ctx = ctx
+        .lock()
+        .map_err(|_| log_boundary_error(None, "context", "failed to acquire host context lock"))?
+        .clone();
+
+    let rt = HostRuntimeBridge::capture()?;
+
+    let result = rt.block_on(async {
+        let plugin_id = require_current_plugin_id(&ctx).await?;
+
+        let settings = ctx.settings.read().await;
+        let locale = settings.get_value("language").unwrap_or_else(|| "en".to_string());
+        drop(settings);
+
+        let plugin_reg = ctx.plugin_registry.read().await;
+        let (resolved, available, default_locale) = match plugin_reg.get(&plugin_id) {
+            Some(plugin) => {
+                let locales = &plugin.manifest.locales;
+                let default_locale = plugin
+                    .manifest
+                    .plugin
+                    .default_locale
+                    .clone()
+                    .unwrap_or_else(|| i18n::FALLBACK_LOCALE.to_string());
+                (
+                    i18n::resolve_locale(locales, &locale, Some(&default_locale))
+                        .map(str::to_string),
+                    i18n::available_locales(locales),
+                    default_locale,
+                )
+            }
+            None => (None, Vec::new(), i18n::FALLBACK_LOCALE.to_string()),
+        };
+        drop(plugin_reg);
+
+        Ok::<_, ExtismError>(serde_json::json!({
+            "locale": locale,
+            "resolved": resolved,
+            "available": available,
+            "defaultLocale": default_locale,
+        }).to_string())
+    })?;
+
+    Ok(result)
+});
+
 // --- Events ---
 
 // Emit an event from a plugin. Always allowed.
@@ -5545,6 +6230,27 @@ pub fn build_host_functions(user_data: UserData<HostContext>) -> Vec<extism::Fun
             user_data.clone(),
             cognia_config_set,
         ),
+        extism::Function::new(
+            "cognia_config_get_typed",
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            cognia_config_get_typed,
+        ),
+        extism::Function::new(
+            "cognia_config_set_typed",
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            cognia_config_set_typed,
+        ),
+        extism::Function::new(
+            "cognia_config_all",
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            cognia_config_all,
+        ),
         extism::Function::new(
             "cognia_env_list",
             [ValType::I64],
@@ -5580,6 +6286,34 @@ pub fn build_host_functions(user_data: UserData<HostContext>) -> Vec<extism::Fun
             user_data.clone(),
             cognia_fs_write,
         ),
+        extism::Function::new(
+            "cognia_storage_get",
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            cognia_storage_get,
+        ),
+        extism::Function::new(
+            "cognia_storage_set",
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            cognia_storage_set,
+        ),
+        extism::Function::new(
+            "cognia_storage_delete",
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            cognia_storage_delete,
+        ),
+        extism::Function::new(
+            "cognia_storage_list",
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            cognia_storage_list,
+        ),
         extism::Function::new(
             "cognia_http_get",
             [ValType::I64],
@@ -5636,6 +6370,20 @@ pub fn build_host_functions(user_data: UserData<HostContext>) -> Vec<extism::Fun
             user_data.clone(),
             cognia_pkg_install,
         ),
+        extism::Function::new(
+            "cognia_pkg_install_start",
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            cognia_pkg_install_start,
+        ),
+        extism::Function::new(
+            "cognia_pkg_operation_status",
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            cognia_pkg_operation_status,
+        ),
         extism::Function::new(
             "cognia_cache_info",
             [ValType::I64],
@@ -5827,6 +6575,13 @@ pub fn build_host_functions(user_data: UserData<HostContext>) -> Vec<extism::Fun
             user_data.clone(),
             cognia_i18n_get_all,
         ),
+        extism::Function::new(
+            "cognia_i18n_get_locales",
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            cognia_i18n_get_locales,
+        ),
         // --- Events & Meta ---
         extism::Function::new(
             "cognia_event_emit",
@@ -6448,7 +7203,7 @@ fn test_build_host_functions_count() {
         let ctx = make_host_context();
         let user_data = create_user_data(ctx);
         let functions = build_host_functions(user_data);
-        assert_eq!(functions.len(), 120);
+        assert_eq!(functions.len(), 129);
     }
 
     #[test]
@@ -6806,4 +7561,132 @@ fn test_ensure_process_exec_permission_uses_existing_permission_gate() {
             ensure_process_exec_permission(&ctx).await.unwrap();
         });
     }
+
+    #[test]
+    fn test_ensure_pkg_install_permission_requires_grant() {
+        let ctx = make_host_context();
+        let rt = tokio::runtime::Runtime::new().unwrap();
+
+        rt.block_on(async {
+            let mut perms = ctx.permissions.write().await;
+            let mut declared = PluginPermissions::default();
+            declared.pkg_install = true;
+            perms.register_plugin("plugin-a", declared);
+        });
+
+        let denied = rt.block_on(async {
+            ctx.set_current_call("plugin-a", "tool_entry").await;
+            ensure_pkg_install_permission(&ctx).await.unwrap_err()
+        });
+        assert!(denied
+            .to_string()
+            .contains("does not have 'pkg_install' permission"));
+
+        rt.block_on(async {
+            ctx.permissions
+                .write()
+                .await
+                .grant_permission("plugin-a", "pkg_install")
+                .unwrap();
+            let plugin_id = ensure_pkg_install_permission(&ctx).await.unwrap();
+            assert_eq!(plugin_id, "plugin-a");
+        });
+    }
+
+    #[test]
+    fn test_resolve_plugin_pkg_provider_rejects_unknown_and_disabled() {
+        let mut registry = ProviderRegistry::new();
+        registry.register_provider(Arc::new(crate::provider::npm::NpmProvider::new()));
+
+        assert!(resolve_plugin_pkg_provider(&registry, "plugin-a", "npm").is_ok());
+
+        let unknown = resolve_plugin_pkg_provider(&registry, "plugin-a", "nope")
+            .err()
+            .unwrap();
+        assert!(unknown.to_string().contains("Unknown provider 'nope'"));
+        assert!(unknown.to_string().contains("npm"));
+
+        registry.set_provider_enabled("npm", false);
+        let disabled = resolve_plugin_pkg_provider(&registry, "plugin-a", "npm")
+            .err()
+            .unwrap();
+        assert!(disabled.to_string().contains("Provider 'npm' is disabled"));
+    }
+
+    fn make_pkg_operation(id: &str, plugin_id: &str) -> PluginPkgOperation {
+        PluginPkgOperation {
+            id: id.to_string(),
+            plugin_id: plugin_id.to_string(),
+            provider: "npm".to_string(),
+            name: "express".to_string(),
+            version: None,
+            status: PluginPkgOperationStatus::Running,
+            stage: None,
+            progress: 0.0,
+            message: None,
+            error: None,
+            receipt: None,
+            started_at: "2026-01-01T00:00:00Z".to_string(),
+            finished_at: None,
+        }
+    }
+
+    #[test]
+    fn test_pkg_operation_progress_and_ownership() {
+        let ctx = make_host_context();
+        let rt = tokio::runtime::Runtime::new().unwrap();
+
+        rt.block_on(async {
+            ctx.insert_pkg_operation(make_pkg_operation("op-1", "plugin-a"))
+                .await;
+
+            let mut event = InstallProgressEvent::new(InstallStage::Downloading, "express");
+            event.progress_percent = 140.0;
+            event.message = "Downloading express".to_string();
+            let updated = ctx
+                .update_pkg_operation("op-1", |op| apply_pkg_progress(op, &event))
+                .await
+                .unwrap();
+            assert_eq!(updated.stage, Some(InstallStage::Downloading));
+            assert_eq!(updated.progress, 100.0);
+            assert_eq!(updated.message.as_deref(), Some("Downloading express"));
+
+            assert!(ctx.pkg_operation("plugin-b", "op-1").await.is_none());
+
+            ctx.update_pkg_operation("op-1", |op| {
+                finish_pkg_operation(op, Err("network down".to_string()), "t1".to_string())
+            })
+            .await;
+            let finished = ctx.pkg_operation("plugin-a", "op-1").await.unwrap();
+            assert_eq!(finished.status, PluginPkgOperationStatus::Failed);
+            assert_eq!(finished.stage, Some(InstallStage::Failed));
+            assert_eq!(finished.error.as_deref(), Some("network down"));
+
+            let json = serde_json::to_value(&finished).unwrap();
+            assert_eq!(json["pluginId"], "plugin-a");
+            assert_eq!(json["status"], "failed");
+        });
+    }
+
+    #[test]
+    fn test_pkg_operation_history_is_bounded() {
+        let ctx = make_host_context();
+        let rt = tokio::runtime::Runtime::new().unwrap();
+
+        rt.block_on(async {
+            for index in 0..PKG_OPERATION_HISTORY_LIMIT + 5 {
+                let mut operation = make_pkg_operation(&format!("op-{}", index), "plugin-a");
+                operation.status = PluginPkgOperationStatus::Completed;
+                operation.finished_at = Some(format!("2026-01-01T00:00:{:02}Z", index));
+                ctx.insert_pkg_operation(operation).await;
+            }
+            ctx.insert_pkg_operation(make_pkg_operation("running", "plugin-a"))
+                .await;
+
+            let operations = ctx.pkg_operations.read().await;
+            assert!(operations.len() <= PKG_OPERATION_HISTORY_LIMIT + 1);
+            assert!(operations.contains_key("running"));
+            assert!(!operations.contains_key("op-0"));
+        });
+    }
 }
@@ -0,0 +1,202 @@
+//! Locale resolution for plugin translations.
+//!
+//! A lookup walks the chain: requested locale, its language-only form
+//! (`zh-TW` -> `zh`, also matching sibling regions such as `zh-CN`), then the
+//! plugin's declared default locale (`en` when none is declared).
+
+use std::collections::HashMap;
+
+pub type PluginLocales = HashMap<String, HashMap<String, String>>;
+
+/// Locale used when a plugin does not declare `default_locale`
+pub const FALLBACK_LOCALE: &str = "en";
+
+fn normalize(tag: &str) -> String {
+    tag.trim().replace('_', "-").to_ascii_lowercase()
+}
+
+fn language(tag: &str) -> &str {
+    tag.split('-').next().unwrap_or(tag)
+}
+
+/// Locale tags to try, most specific first, without duplicates
+pub fn fallback_chain(requested: &str, default_locale: Option<&str>) -> Vec<String> {
+    let requested = normalize(requested);
+    let default_locale = normalize(default_locale.unwrap_or(FALLBACK_LOCALE));
+    let mut chain: Vec<String> = Vec::with_capacity(3);
+    for tag in [
+        requested.clone(),
+        language(&requested).to_string(),
+        default_locale,
+    ] {
+        if !tag.is_empty() && !chain.contains(&tag) {
+            chain.push(tag);
+        }
+    }
+    chain
+}
+
+/// Key in `locales` matching `tag`. A language-only tag also matches a
+/// regional variant when the bare language is not shipped.
+fn find_locale<'a>(locales: &'a PluginLocales, tag: &str) -> Option<&'a str> {
+    let mut keys: Vec<&String> = locales.keys().collect();
+    keys.sort();
+    if let Some(key) = keys.iter().find(|key| normalize(key) == tag) {
+        return Some(key.as_str());
+    }
+    if tag.contains('-') {
+        return None;
+    }
+    keys.into_iter()
+        .find(|key| language(&normalize(key)) == tag)
+        .map(String::as_str)
+}
+
+/// Locale keys of `locales` in fallback order for `requested`
+pub fn resolve_chain<'a>(
+    locales: &'a PluginLocales,
+    requested: &str,
+    default_locale: Option<&str>,
+) -> Vec<&'a str> {
+    let mut resolved = Vec::new();
+    for tag in fallback_chain(requested, default_locale) {
+        if let Some(key) = find_locale(locales, &tag) {
+            if !resolved.contains(&key) {
+                resolved.push(key);
+            }
+        }
+    }
+    resolved
+}
+
+/// The locale a plugin's strings resolve to for `requested`
+pub fn resolve_locale<'a>(
+    locales: &'a PluginLocales,
+    requested: &str,
+    default_locale: Option<&str>,
+) -> Option<&'a str> {
+    resolve_chain(locales, requested, default_locale)
+        .into_iter()
+        .next()
+}
+
+/// Look `key` up along the fallback chain
+pub fn lookup<'a>(
+    locales: &'a PluginLocales,
+    requested: &str,
+    default_locale: Option<&str>,
+    key: &str,
+) -> Option<&'a str> {
+    resolve_chain(locales, requested, default_locale)
+        .into_iter()
+        .find_map(|locale| locales.get(locale)?.get(key))
+        .map(String::as_str)
+}
+
+/// All strings visible for `requested`: more specific locales override
+/// the ones further down the chain.
+pub fn merged_strings(
+    locales: &PluginLocales,
+    requested: &str,
+    default_locale: Option<&str>,
+) -> HashMap<String, String> {
+    let mut merged = HashMap::new();
+    for locale in resolve_chain(locales, requested, default_locale)
+        .into_iter()
+        .rev()
+    {
+        if let Some(strings) = locales.get(locale) {
+            merged.extend(strings.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+    }
+    merged
+}
+
+/// Locale keys shipped by a plugin, sorted
+pub fn available_locales(locales: &PluginLocales) -> Vec<String> {
+    let mut available: Vec<String> = locales.keys().cloned().collect();
+    available.sort();
+    available
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locales(entries: &[(&str, &[(&str, &str)])]) -> PluginLocales {
+        entries
+            .iter()
+            .map(|(locale, strings)| {
+                (
+                    locale.to_string(),
+                    strings
+                        .iter()
+                        .map(|(k, v)| (k.to_string(), v.to_string()))
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fallback_chain_order() {
+        assert_eq!(fallback_chain("zh-TW", None), vec!["zh-tw", "zh", "en"]);
+        assert_eq!(fallback_chain("fr", Some("de")), vec!["fr", "de"]);
+        assert_eq!(fallback_chain("en_US", None), vec!["en-us", "en"]);
+    }
+
+    #[test]
+    fn test_region_falls_back_to_language() {
+        let locales = locales(&[
+            ("en", &[("greeting", "Hello")]),
+            ("zh", &[("greeting", "你好")]),
+        ]);
+        assert_eq!(resolve_locale(&locales, "zh-TW", None), Some("zh"));
+        assert_eq!(lookup(&locales, "zh-TW", None, "greeting"), Some("你好"));
+    }
+
+    #[test]
+    fn test_language_matches_regional_variant() {
+        let locales = locales(&[
+            ("en", &[("greeting", "Hello")]),
+            ("zh-CN", &[("greeting", "你好")]),
+        ]);
+        assert_eq!(resolve_locale(&locales, "zh", None), Some("zh-CN"));
+        assert_eq!(resolve_locale(&locales, "zh-TW", None), Some("zh-CN"));
+    }
+
+    #[test]
+    fn test_unknown_locale_uses_declared_default() {
+        let locales = locales(&[
+            ("en", &[("greeting", "Hello")]),
+            ("zh-CN", &[("greeting", "你好")]),
+        ]);
+        assert_eq!(
+            lookup(&locales, "fr", Some("zh-CN"), "greeting"),
+            Some("你好")
+        );
+        assert_eq!(lookup(&locales, "fr", None, "greeting"), Some("Hello"));
+    }
+
+    #[test]
+    fn test_missing_key_falls_through_chain() {
+        let locales = locales(&[
+            ("en", &[("greeting", "Hello"), ("farewell", "Bye")]),
+            ("zh", &[("greeting", "你好")]),
+        ]);
+        assert_eq!(lookup(&locales, "zh-TW", None, "farewell"), Some("Bye"));
+        assert_eq!(lookup(&locales, "zh-TW", None, "missing"), None);
+
+        let merged = merged_strings(&locales, "zh-TW", None);
+        assert_eq!(merged["greeting"], "你好");
+        assert_eq!(merged["farewell"], "Bye");
+    }
+
+    #[test]
+    fn test_no_locales_resolves_nothing() {
+        let locales = PluginLocales::new();
+        assert_eq!(resolve_locale(&locales, "en", None), None);
+        assert!(merged_strings(&locales, "en", None).is_empty());
+        assert!(available_locales(&locales).is_empty());
+    }
+}
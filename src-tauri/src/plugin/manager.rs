@@ -1,15 +1,28 @@
+use crate::cache::MetadataCache;
 use crate::config::Settings;
 use crate::download::{DownloadManager, DownloadState, DownloadTask};
 use crate::error::{CogniaError, CogniaResult};
 use crate::plugin::contract::evaluate_manifest_compatibility;
+use crate::plugin::dev_watch::{DevWatcher, PluginDevWatchInfo};
 use crate::plugin::extension_points::get_tool_plugin_point;
 use crate::plugin::host_functions::{EmittedPluginLog, EmittedPluginUiEffect, HostContext};
 use crate::plugin::loader::PluginLoader;
 use crate::plugin::manifest::PluginManifest;
-use crate::plugin::permissions::{PermissionEnforcementMode, PermissionManager};
+use crate::plugin::marketplace::{
+    entry_matches_filter, parse_index_document, permission_escalation, verify_index_envelope,
+    CachedMarketplaceIndex, MarketplaceFilter, MarketplaceIndexEntry, MarketplaceIndexSummary,
+    MarketplaceInstallReport, MarketplacePluginListing, VerifiedMarketplaceIndex,
+    MARKETPLACE_INDEX_CACHE_KEY, MARKETPLACE_INDEX_TTL_SECS,
+};
+use crate::plugin::permissions::{
+    declared_permission_names, PermissionEnforcementMode, PermissionManager,
+};
 use crate::plugin::registry::{
     PluginInfo, PluginRegistry, PluginSource, PluginToolInfo, BUILTIN_MARKER_FILE,
 };
+use crate::plugin::storage::{
+    quota_bytes_from_mb, PluginDataDirInfo, PluginStorageInspection, PluginStore,
+};
 use crate::provider::registry::ProviderRegistry;
 use crate::resolver::version::Version;
 use serde::{Deserialize, Serialize};
@@ -208,6 +221,9 @@ pub struct PluginUpdateInfo {
     pub latest_version: String,
     pub download_url: String,
     pub changelog: Option<String>,
+    /// Permissions the new version declares that the installed one does not.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub new_permissions: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -321,6 +337,7 @@ pub struct PluginManager {
     builtin_catalog_ids: HashSet<String>,
     builtin_source_override: Option<PathBuf>,
     download_manager: Option<Arc<RwLock<DownloadManager>>>,
+    dev_watchers: HashMap<String, DevWatcher>,
 }
 
 impl PluginManager {
@@ -357,6 +374,7 @@ pub fn new(base_dir: &Path, deps: PluginDeps) -> Self {
             builtin_catalog_ids: HashSet::new(),
             builtin_source_override: None,
             download_manager: deps.download_manager,
+            dev_watchers: HashMap::new(),
         }
     }
 
@@ -1517,8 +1535,368 @@ pub async fn install_from_marketplace(&mut self, store_id: &str) -> CogniaResult
         Ok(report.plugin_id)
     }
 
+    async fn marketplace_settings(&self) -> (String, Vec<String>, bool) {
+        let settings = self.settings.read().await;
+        (
+            settings.plugin.marketplace_index_url.trim().to_string(),
+            settings.plugin.marketplace_trusted_keys.clone(),
+            settings.plugin.developer_mode,
+        )
+    }
+
+    async fn open_marketplace_cache(&self) -> CogniaResult<MetadataCache> {
+        let cache_dir = self.settings.read().await.get_cache_dir();
+        MetadataCache::open_with_ttl(&cache_dir, MARKETPLACE_INDEX_TTL_SECS).await
+    }
+
+    fn accept_marketplace_index(
+        cached: &CachedMarketplaceIndex,
+        trusted_keys: &[String],
+        developer_mode: bool,
+    ) -> CogniaResult<VerifiedMarketplaceIndex> {
+        let verified = verify_index_envelope(&cached.envelope, trusted_keys)?;
+        if !verified.signed && !developer_mode {
+            return Err(CogniaError::Plugin(format!(
+                "Marketplace index from '{}' is unsigned. Enable plugin.developer_mode to use unsigned indexes.",
+                cached.url
+            )));
+        }
+        Ok(verified)
+    }
+
+    /// Fetch the marketplace index, verify it and store it in the metadata cache.
+    pub async fn refresh_marketplace_index(&self) -> CogniaResult<MarketplaceIndexSummary> {
+        let (url, trusted_keys, developer_mode) = self.marketplace_settings().await;
+        if url.is_empty() {
+            return Err(CogniaError::Config(
+                "No marketplace index URL configured (plugin.marketplace_index_url).".to_string(),
+            ));
+        }
+
+        let response = reqwest::Client::new()
+            .get(&url)
+            .timeout(Duration::from_secs(30))
+            .send()
+            .await
+            .map_err(|e| {
+                CogniaError::Plugin(format!("Failed to fetch marketplace index: {}", e))
+            })?;
+        if !response.status().is_success() {
+            return Err(CogniaError::Plugin(format!(
+                "Failed to fetch marketplace index: HTTP {}",
+                response.status()
+            )));
+        }
+        let raw = response
+            .text()
+            .await
+            .map_err(|e| CogniaError::Plugin(format!("Failed to read marketplace index: {}", e)))?;
+
+        let cached = CachedMarketplaceIndex {
+            url: url.clone(),
+            envelope: parse_index_document(&raw)?,
+            fetched_at: chrono::Utc::now().to_rfc3339(),
+        };
+        let verified = Self::accept_marketplace_index(&cached, &trusted_keys, developer_mode)?;
+
+        let mut cache = self.open_marketplace_cache().await?;
+        cache.set(MARKETPLACE_INDEX_CACHE_KEY, &cached).await?;
+
+        Ok(MarketplaceIndexSummary {
+            url,
+            plugin_count: verified.index.plugins.len(),
+            signed: verified.signed,
+            key_id: verified.key_id,
+            generated_at: verified.index.generated_at,
+            fetched_at: cached.fetched_at,
+        })
+    }
+
+    /// Load the cached index (refreshing it when missing or stale) and re-verify it
+    /// against the current trust settings.
+    async fn load_marketplace_index(&self) -> CogniaResult<VerifiedMarketplaceIndex> {
+        let (url, trusted_keys, developer_mode) = self.marketplace_settings().await;
+        let mut cache = self.open_marketplace_cache().await?;
+        let cached = cache
+            .get::<CachedMarketplaceIndex>(MARKETPLACE_INDEX_CACHE_KEY)
+            .await
+            .ok()
+            .flatten()
+            .filter(|cached| cached.data.url == url);
+
+        let entry = match cached {
+            Some(cached) if !cached.is_stale => cached.data,
+            stale => match self.refresh_marketplace_index().await {
+                Ok(_) => cache
+                    .get::<CachedMarketplaceIndex>(MARKETPLACE_INDEX_CACHE_KEY)
+                    .await?
+                    .map(|cached| cached.data)
+                    .ok_or_else(|| {
+                        CogniaError::Plugin("Marketplace index cache is unavailable.".to_string())
+                    })?,
+                Err(error) => match stale {
+                    Some(cached) => {
+                        log::warn!("Using stale marketplace index: {}", error);
+                        cached.data
+                    }
+                    None => return Err(error),
+                },
+            },
+        };
+
+        Self::accept_marketplace_index(&entry, &trusted_keys, developer_mode)
+    }
+
+    /// List marketplace plugins with their installed state.
+    pub async fn list_marketplace(
+        &self,
+        filter: &MarketplaceFilter,
+    ) -> CogniaResult<Vec<MarketplacePluginListing>> {
+        let verified = self.load_marketplace_index().await?;
+        let reg = self.registry.read().await;
+        Ok(verified
+            .index
+            .plugins
+            .into_iter()
+            .filter(|entry| entry_matches_filter(entry, filter))
+            .map(|entry| {
+                let installed_version = reg
+                    .get(&entry.id)
+                    .map(|plugin| plugin.manifest.plugin.version.clone());
+                let update_available = installed_version
+                    .as_deref()
+                    .is_some_and(|installed| Self::is_newer_version(&entry.version, installed));
+                MarketplacePluginListing {
+                    entry,
+                    installed_version,
+                    update_available,
+                    signed: verified.signed,
+                }
+            })
+            .collect())
+    }
+
+    fn is_newer_version(candidate: &str, current: &str) -> bool {
+        let candidate: Version = candidate.parse().unwrap_or(Version::new(0, 0, 0));
+        let current: Version = current.parse().unwrap_or(Version::new(0, 0, 0));
+        candidate > current
+    }
+
+    /// Compare installed plugins against the marketplace index.
+    pub async fn check_marketplace_updates(&self) -> CogniaResult<Vec<PluginUpdateInfo>> {
+        let verified = self.load_marketplace_index().await?;
+        let reg = self.registry.read().await;
+        let mut updates = Vec::new();
+        for entry in verified.index.plugins {
+            let Some(plugin) = reg.get(&entry.id) else {
+                continue;
+            };
+            let current_version = plugin.manifest.plugin.version.clone();
+            if !Self::is_newer_version(&entry.version, &current_version) {
+                continue;
+            }
+            let installed_permissions = declared_permission_names(&plugin.manifest.permissions);
+            updates.push(PluginUpdateInfo {
+                plugin_id: entry.id,
+                current_version,
+                latest_version: entry.version,
+                download_url: entry.download_url,
+                changelog: entry.changelog,
+                new_permissions: permission_escalation(&installed_permissions, &entry.permissions),
+            });
+        }
+        Ok(updates)
+    }
+
+    async fn download_marketplace_archive(
+        &self,
+        entry: &MarketplaceIndexEntry,
+        destination: &Path,
+    ) -> CogniaResult<()> {
+        if self.download_manager.is_some() {
+            let listing = MarketplaceCatalogListing {
+                id: entry.id.clone(),
+                plugin_id: entry.id.clone(),
+                version: entry.version.clone(),
+                source: MarketplaceCatalogSource {
+                    store_id: entry.id.clone(),
+                    plugin_dir: String::new(),
+                    artifact: "plugin.wasm".to_string(),
+                    checksum_sha256: entry.sha256.clone(),
+                    download_url: Some(entry.download_url.clone()),
+                    mirror_urls: Vec::new(),
+                    size_bytes: None,
+                },
+            };
+            self.queue_marketplace_download(&listing, "install", &entry.download_url, destination)
+                .await?;
+            return Ok(());
+        }
+
+        let response = reqwest::Client::new()
+            .get(&entry.download_url)
+            .send()
+            .await
+            .map_err(|e| CogniaError::Plugin(format!("Failed to download plugin: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(CogniaError::Plugin(format!(
+                "Failed to download plugin: HTTP {}",
+                response.status()
+            )));
+        }
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| CogniaError::Plugin(format!("Failed to read download: {}", e)))?;
+        tokio::fs::write(destination, &bytes)
+            .await
+            .map_err(|e| CogniaError::Plugin(format!("Failed to write download: {}", e)))
+    }
+
+    /// Install or upgrade a plugin listed in the signed marketplace index.
+    ///
+    /// Permissions the new version adds over the installed one stay revoked unless
+    /// they are listed in `grant`; previous grants and denials carry over.
+    pub async fn install_from_marketplace_index(
+        &mut self,
+        plugin_id: &str,
+        grant: &[String],
+    ) -> CogniaResult<MarketplaceInstallReport> {
+        let verified = self.load_marketplace_index().await?;
+        let entry = verified
+            .index
+            .plugins
+            .into_iter()
+            .find(|entry| entry.id == plugin_id)
+            .ok_or_else(|| {
+                CogniaError::Plugin(format!(
+                    "Plugin '{}' is not listed in the marketplace index.",
+                    plugin_id
+                ))
+            })?;
+
+        let temp_dir = tempfile::tempdir()
+            .map_err(|e| CogniaError::Plugin(format!("Failed to create temp dir: {}", e)))?;
+        let archive_path = temp_dir.path().join("marketplace-package.zip");
+        self.download_marketplace_archive(&entry, &archive_path)
+            .await?;
+
+        let archive_checksum = Self::sha256_file(&archive_path).await?;
+        if !archive_checksum.eq_ignore_ascii_case(&entry.sha256) {
+            return Err(CogniaError::Plugin(format!(
+                "Marketplace checksum mismatch for '{}': expected {}, got {}",
+                entry.id, entry.sha256, archive_checksum
+            )));
+        }
+
+        let extract_root = temp_dir.path().join("extracted");
+        let archive_file = std::fs::File::open(&archive_path).map_err(|e| {
+            CogniaError::Plugin(format!("Failed to open downloaded archive: {}", e))
+        })?;
+        let mut archive = zip::ZipArchive::new(archive_file)
+            .map_err(|e| CogniaError::Plugin(format!("Invalid marketplace zip file: {}", e)))?;
+        archive.extract(&extract_root).map_err(|e| {
+            CogniaError::Plugin(format!("Failed to extract marketplace zip package: {}", e))
+        })?;
+        let source_dir = Self::find_plugin_package_root(&extract_root).ok_or_else(|| {
+            CogniaError::Plugin(
+                "Marketplace package is missing required plugin.toml/plugin.wasm files."
+                    .to_string(),
+            )
+        })?;
+
+        let manifest = PluginManifest::from_file(&source_dir.join("plugin.toml"))?;
+        let next_permissions = declared_permission_names(&manifest.permissions);
+        if let Some(unlisted) = next_permissions
+            .iter()
+            .find(|permission| !entry.permissions.contains(permission))
+        {
+            return Err(CogniaError::Plugin(format!(
+                "Marketplace package '{}' declares permission '{}' that the index does not list.",
+                entry.id, unlisted
+            )));
+        }
+
+        let (previous_version, previous_permissions) = {
+            let reg = self.registry.read().await;
+            match reg.get(&entry.id) {
+                Some(plugin) => (
+                    Some(plugin.manifest.plugin.version.clone()),
+                    declared_permission_names(&plugin.manifest.permissions),
+                ),
+                None => (None, Vec::new()),
+            }
+        };
+        let previous_state = {
+            let perms = self.permissions.read().await;
+            perms.get_state(&entry.id).cloned()
+        };
+        let escalated = if previous_version.is_some() {
+            permission_escalation(&previous_permissions, &next_permissions)
+        } else {
+            Vec::new()
+        };
+
+        let installed_id = self
+            .install_from_dir(
+                &source_dir,
+                PluginSource::Store {
+                    store_id: entry.id.clone(),
+                },
+                true,
+                Some(entry.id.as_str()),
+                Some(entry.version.as_str()),
+                None,
+            )
+            .await?;
+
+        let mut pending_permissions = Vec::new();
+        {
+            let mut perms = self.permissions.write().await;
+            if let Some(previous) = &previous_state {
+                for permission in &previous.granted {
+                    if next_permissions.contains(permission)
+                        && !previous.denied.contains(permission)
+                    {
+                        perms.grant_permission(&installed_id, permission)?;
+                    }
+                }
+                for permission in &previous.denied {
+                    perms.revoke_permission(&installed_id, permission)?;
+                }
+            }
+            for permission in &escalated {
+                if grant.contains(permission) {
+                    perms.grant_permission(&installed_id, permission)?;
+                } else {
+                    perms.revoke_permission(&installed_id, permission)?;
+                    pending_permissions.push(permission.clone());
+                }
+            }
+        }
+        let _ = self.save_state().await;
+
+        if !pending_permissions.is_empty() {
+            log::info!(
+                "Plugin '{}' {} requires re-grant of new permissions: {}",
+                installed_id,
+                entry.version,
+                pending_permissions.join(", ")
+            );
+        }
+
+        Ok(MarketplaceInstallReport {
+            plugin_id: installed_id,
+            version: entry.version,
+            previous_version,
+            pending_permissions,
+        })
+    }
+
     /// Uninstall a plugin
     pub async fn uninstall(&mut self, plugin_id: &str) -> CogniaResult<()> {
+        self.stop_dev_watch(plugin_id);
+
         // Call optional lifecycle hook before unload
         self.loader
             .call_if_exists(plugin_id, "cognia_on_uninstall", "{}")
@@ -1527,6 +1905,12 @@ pub async fn uninstall(&mut self, plugin_id: &str) -> CogniaResult<()> {
         // Unload WASM
         self.loader.unload(plugin_id);
 
+        // Delete the plugin's key-value store
+        let data_dir = self.get_plugin_data_dir(plugin_id).await;
+        if let Err(e) = crate::plugin::storage::remove_store(&data_dir).await {
+            log::warn!("Failed to delete storage for plugin '{}': {}", plugin_id, e);
+        }
+
         // Unregister permissions
         let mut perms = self.permissions.write().await;
         perms.unregister_plugin(plugin_id);
@@ -1607,6 +1991,8 @@ pub async fn disable(&mut self, plugin_id: &str) -> CogniaResult<()> {
             plugin.enabled = false;
         }
 
+        self.stop_dev_watch(plugin_id);
+
         // Call optional lifecycle hook before unload
         self.loader
             .call_if_exists(plugin_id, "cognia_on_disable", "{}")
@@ -1642,6 +2028,174 @@ pub async fn reload(&mut self, plugin_id: &str) -> CogniaResult<()> {
         self.loader.reload(plugin_id, &wasm_path)
     }
 
+    /// Validate a development watch request. Only locally imported plugins can
+    /// be watched; registry, URL and built-in installs are managed by Cognia.
+    pub async fn prepare_dev_watch(
+        &self,
+        plugin_id: &str,
+        wasm_path: &Path,
+    ) -> CogniaResult<PluginDevWatchInfo> {
+        let source_dir = {
+            let reg = self.registry.read().await;
+            let plugin = reg
+                .get(plugin_id)
+                .ok_or_else(|| CogniaError::Plugin(format!("Plugin '{}' not found", plugin_id)))?;
+            if !plugin.enabled {
+                return Err(CogniaError::Plugin(format!(
+                    "Plugin '{}' is disabled, cannot watch",
+                    plugin_id
+                )));
+            }
+            match &plugin.source {
+                PluginSource::Local { path } => PathBuf::from(path),
+                _ => {
+                    return Err(CogniaError::Plugin(format!(
+                        "Plugin '{}' was not imported locally; dev watch is only available for local plugins",
+                        plugin_id
+                    )))
+                }
+            }
+        };
+
+        if !wasm_path.is_file() {
+            return Err(CogniaError::Plugin(format!(
+                "WASM file not found: {}",
+                wasm_path.display()
+            )));
+        }
+
+        let manifest_path = source_dir.join("plugin.toml");
+        Ok(PluginDevWatchInfo {
+            plugin_id: plugin_id.to_string(),
+            wasm_path: wasm_path.display().to_string(),
+            manifest_path: manifest_path
+                .is_file()
+                .then(|| manifest_path.display().to_string()),
+            started_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// Register a running dev watch, replacing any previous watch for the plugin
+    pub(crate) fn start_dev_watch(&mut self, watcher: DevWatcher) {
+        let plugin_id = watcher.info.plugin_id.clone();
+        if self
+            .dev_watchers
+            .insert(plugin_id.clone(), watcher)
+            .is_some()
+        {
+            log::info!("Replaced dev watch for plugin '{}'", plugin_id);
+        }
+    }
+
+    /// Stop the dev watch for a plugin. Returns whether one was active.
+    pub fn stop_dev_watch(&mut self, plugin_id: &str) -> bool {
+        self.dev_watchers.remove(plugin_id).is_some()
+    }
+
+    /// Stop every dev watch (app shutdown). Returns how many were active.
+    pub fn stop_all_dev_watches(&mut self) -> usize {
+        let count = self.dev_watchers.len();
+        self.dev_watchers.clear();
+        count
+    }
+
+    /// List active dev watches
+    pub fn list_dev_watches(&self) -> Vec<PluginDevWatchInfo> {
+        let mut watches: Vec<_> = self.dev_watchers.values().map(|w| w.info.clone()).collect();
+        watches.sort_by(|a, b| a.plugin_id.cmp(&b.plugin_id));
+        watches
+    }
+
+    /// Copy a fresh dev build (and manifest, if watched) over the installed
+    /// plugin and reload it
+    pub async fn reload_dev_build(
+        &mut self,
+        plugin_id: &str,
+        wasm_path: &Path,
+        manifest_path: Option<&Path>,
+    ) -> CogniaResult<()> {
+        let manifest = match manifest_path {
+            Some(path) => {
+                let manifest = PluginManifest::from_file(path)?;
+                if manifest.plugin.id != plugin_id {
+                    return Err(CogniaError::Plugin(format!(
+                        "Manifest id changed from '{}' to '{}'; re-import the plugin instead",
+                        plugin_id, manifest.plugin.id
+                    )));
+                }
+                let compatibility = evaluate_manifest_compatibility(&manifest);
+                if !compatibility.compatible {
+                    return Err(incompatible_plugin_error(
+                        plugin_id,
+                        compatibility.reason.as_deref(),
+                    ));
+                }
+                Some(manifest)
+            }
+            None => None,
+        };
+
+        let (installed_wasm, plugin_dir) = {
+            let reg = self.registry.read().await;
+            let plugin = reg
+                .get(plugin_id)
+                .ok_or_else(|| CogniaError::Plugin(format!("Plugin '{}' not found", plugin_id)))?;
+            if !plugin.enabled {
+                return Err(CogniaError::Plugin(format!(
+                    "Plugin '{}' is disabled, cannot reload",
+                    plugin_id
+                )));
+            }
+            (plugin.wasm_path.clone(), plugin.plugin_dir.clone())
+        };
+
+        if wasm_path != installed_wasm.as_path() {
+            tokio::fs::copy(wasm_path, &installed_wasm)
+                .await
+                .map_err(|e| {
+                    CogniaError::Plugin(format!(
+                        "Failed to copy {} to {}: {}",
+                        wasm_path.display(),
+                        installed_wasm.display(),
+                        e
+                    ))
+                })?;
+        }
+
+        if let (Some(manifest), Some(path)) = (manifest, manifest_path) {
+            let installed_manifest = plugin_dir.join("plugin.toml");
+            if path != installed_manifest.as_path() {
+                tokio::fs::copy(path, &installed_manifest)
+                    .await
+                    .map_err(|e| {
+                        CogniaError::Plugin(format!("Failed to copy plugin manifest: {}", e))
+                    })?;
+            }
+
+            let permissions_changed = {
+                let mut reg = self.registry.write().await;
+                match reg.get_mut(plugin_id) {
+                    Some(plugin) => {
+                        let changed = serde_json::to_value(&plugin.manifest.permissions).ok()
+                            != serde_json::to_value(&manifest.permissions).ok();
+                        plugin.manifest = manifest.clone();
+                        plugin.updated_at = Some(chrono::Utc::now());
+                        changed
+                    }
+                    None => false,
+                }
+            };
+            // Re-registering resets grants, so only do it when the declared
+            // permissions actually changed
+            if permissions_changed {
+                let mut perms = self.permissions.write().await;
+                perms.register_plugin(plugin_id, manifest.permissions);
+            }
+        }
+
+        self.loader.reload(plugin_id, &installed_wasm)
+    }
+
     /// Call a tool function on a loaded plugin with circuit breaker protection
     pub async fn call_tool(
         &mut self,
@@ -1901,6 +2455,63 @@ pub async fn dispatch_event(&mut self, event_name: &str, payload: &serde_json::V
             .await;
     }
 
+    /// Deliver a host event to a single enabled plugin, regardless of its
+    /// `listen_events` declaration. Used for callbacks the plugin asked for,
+    /// such as progress of a package operation it started.
+    pub async fn dispatch_event_to_plugin(
+        &mut self,
+        plugin_id: &str,
+        event_name: &str,
+        payload: &serde_json::Value,
+    ) -> bool {
+        let wasm_path = {
+            let reg = self.registry.read().await;
+            match reg.get(plugin_id) {
+                Some(plugin) if plugin.enabled => plugin.wasm_path.clone(),
+                _ => return false,
+            }
+        };
+
+        if !self.loader.is_loaded(plugin_id) {
+            if let Err(e) = self.loader.load(plugin_id, &wasm_path) {
+                log::warn!(
+                    "[plugin-runtime][plugin:{}][operation:dispatch_event_to_plugin][stage:listener-load] failed to lazy-load plugin: {}",
+                    plugin_id,
+                    e
+                );
+                return false;
+            }
+        }
+
+        let input = serde_json::json!({
+            "event": event_name,
+            "payload": payload,
+            "sourcePluginId": serde_json::Value::Null,
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+        })
+        .to_string();
+        let delivered = self
+            .loader
+            .call_if_exists(plugin_id, "cognia_on_event", &input)
+            .await
+            .is_some();
+
+        for log_record in self.loader.drain_emitted_logs().await {
+            self.dispatch_log_with_meta(log_record).await;
+        }
+        for event in self.loader.drain_emitted_events().await {
+            self.dispatch_event_with_meta(
+                &event.event_name,
+                &event.payload,
+                Some(&event.source_plugin_id),
+                Some(&event.timestamp),
+            )
+            .await;
+        }
+
+        delivered
+    }
+
     fn matches_log_listener_filter(filter: &str, source_type: &str) -> bool {
         filter == "*" || filter == source_type
     }
@@ -2106,46 +2717,36 @@ pub async fn get_plugin_settings_values(
         plugin_id: &str,
     ) -> CogniaResult<HashMap<String, serde_json::Value>> {
         let data_dir = self.get_plugin_data_dir(plugin_id).await;
-        let settings_path = data_dir.join("settings.json");
-        if !settings_path.exists() {
-            return Ok(HashMap::new());
-        }
-        let content = tokio::fs::read_to_string(&settings_path)
-            .await
-            .map_err(|e| CogniaError::Plugin(format!("Failed to read settings: {}", e)))?;
-        serde_json::from_str(&content)
-            .map_err(|e| CogniaError::Plugin(format!("Invalid settings JSON: {}", e)))
+        read_plugin_settings_file(&data_dir).await
     }
 
-    /// Set a single setting value for a plugin
+    /// Get setting values merged with declared defaults, flagging schema drift
+    pub async fn get_plugin_settings_resolved(
+        &self,
+        plugin_id: &str,
+    ) -> CogniaResult<crate::plugin::manifest::ResolvedPluginSettings> {
+        let schema = self.get_plugin_settings_schema(plugin_id).await?;
+        let stored = self.get_plugin_settings_values(plugin_id).await?;
+        Ok(crate::plugin::manifest::resolve_setting_values(
+            &schema, &stored,
+        ))
+    }
+
+    /// Set a single setting value for a plugin.
+    ///
+    /// When the plugin declares a settings schema, the key must be declared and the
+    /// value must satisfy its type; the normalized value is stored and returned.
     pub async fn set_plugin_setting(
         &mut self,
         plugin_id: &str,
         key: &str,
         value: serde_json::Value,
-    ) -> CogniaResult<()> {
+    ) -> CogniaResult<serde_json::Value> {
+        let schema = self.get_plugin_settings_schema(plugin_id).await?;
+        let value = validate_plugin_setting(&schema, key, &value)?;
         let data_dir = self.get_plugin_data_dir(plugin_id).await;
-        let _ = tokio::fs::create_dir_all(&data_dir).await;
-        let settings_path = data_dir.join("settings.json");
-
-        let mut settings: HashMap<String, serde_json::Value> = if settings_path.exists() {
-            let content = tokio::fs::read_to_string(&settings_path)
-                .await
-                .unwrap_or_default();
-            serde_json::from_str(&content).unwrap_or_default()
-        } else {
-            HashMap::new()
-        };
-
-        settings.insert(key.to_string(), value);
-
-        let content = serde_json::to_string_pretty(&settings)
-            .map_err(|e| CogniaError::Plugin(format!("Failed to serialize settings: {}", e)))?;
-        tokio::fs::write(&settings_path, content)
-            .await
-            .map_err(|e| CogniaError::Plugin(format!("Failed to write settings: {}", e)))?;
-
-        Ok(())
+        write_plugin_setting(&data_dir, key, value.clone()).await?;
+        Ok(value)
     }
 
     /// Export a plugin's directory + data as a zip file
@@ -2391,6 +2992,71 @@ pub async fn get_plugin_data_dir(&self, plugin_id: &str) -> PathBuf {
         perms.get_plugin_data_dir(plugin_id)
     }
 
+    /// Open a plugin's key-value store with the configured quota
+    pub async fn plugin_store(&self, plugin_id: &str) -> PluginStore {
+        let data_dir = self.get_plugin_data_dir(plugin_id).await;
+        let quota_mb = self.settings.read().await.plugin.storage_quota_mb;
+        PluginStore::new(&data_dir, quota_bytes_from_mb(quota_mb))
+    }
+
+    /// Get plugin data directory together with its storage usage vs quota
+    pub async fn get_plugin_data_dir_info(
+        &self,
+        plugin_id: &str,
+    ) -> CogniaResult<PluginDataDirInfo> {
+        let data_dir = self.get_plugin_data_dir(plugin_id).await;
+        let storage = self
+            .plugin_store(plugin_id)
+            .await
+            .usage()
+            .await
+            .map_err(|e| CogniaError::Plugin(e.to_string()))?;
+        Ok(PluginDataDirInfo {
+            path: data_dir.display().to_string(),
+            storage,
+        })
+    }
+
+    /// List everything a plugin has put in its key-value store
+    pub async fn inspect_plugin_storage(
+        &self,
+        plugin_id: &str,
+    ) -> CogniaResult<PluginStorageInspection> {
+        let store = self.plugin_store(plugin_id).await;
+        let usage = store
+            .usage()
+            .await
+            .map_err(|e| CogniaError::Plugin(e.to_string()))?;
+        let entries = store
+            .inspect()
+            .await
+            .map_err(|e| CogniaError::Plugin(e.to_string()))?;
+        Ok(PluginStorageInspection {
+            plugin_id: plugin_id.to_string(),
+            usage,
+            entries,
+        })
+    }
+
+    /// Delete keys from a plugin's key-value store (all keys when `keys` is None)
+    pub async fn clear_plugin_storage(
+        &self,
+        plugin_id: &str,
+        keys: Option<Vec<String>>,
+    ) -> CogniaResult<usize> {
+        let store = self.plugin_store(plugin_id).await;
+        let removed = store
+            .clear(keys.as_deref())
+            .await
+            .map_err(|e| CogniaError::Plugin(e.to_string()))?;
+        log::info!(
+            "Cleared {} storage key(s) for plugin '{}'",
+            removed,
+            plugin_id
+        );
+        Ok(removed)
+    }
+
     /// Grant a permission to a plugin
     pub async fn grant_permission(&self, plugin_id: &str, permission: &str) -> CogniaResult<()> {
         let mut perms = self.permissions.write().await;
@@ -2493,6 +3159,7 @@ pub async fn check_update(&self, plugin_id: &str) -> CogniaResult<Option<PluginU
                 latest_version: info.version,
                 download_url: info.download_url,
                 changelog: info.changelog,
+                new_permissions: Vec::new(),
             }))
         } else {
             Ok(None)
@@ -2743,6 +3410,7 @@ fn make_listener_manifest(plugin_id: &str, events: Vec<&str>) -> PluginManifest
                 update_url: None,
                 listen_events: events.into_iter().map(|e| e.to_string()).collect(),
                 listen_logs: vec![],
+                default_locale: None,
             },
             tools: vec![],
             permissions: PluginPermissions::default(),
@@ -3029,6 +3697,90 @@ async fn test_dispatch_event_no_listeners_is_noop() {
             .await;
     }
 
+    #[tokio::test]
+    async fn test_dispatch_event_to_unknown_plugin_is_not_delivered() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut manager = make_test_manager(temp_dir.path());
+
+        let delivered = manager
+            .dispatch_event_to_plugin(
+                "com.example.missing",
+                "pkg_install_progress",
+                &serde_json::json!({ "id": "op-1" }),
+            )
+            .await;
+        assert!(!delivered);
+    }
+
+    #[tokio::test]
+    async fn test_prepare_dev_watch_rejects_registry_plugins() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manager = make_test_manager(temp_dir.path());
+        let wasm_path = temp_dir.path().join("plugin.wasm");
+        tokio::fs::write(&wasm_path, b"\0asm").await.unwrap();
+
+        {
+            let mut reg = manager.registry.write().await;
+            reg.register(
+                make_listener_manifest("com.example.store", vec![]),
+                wasm_path.clone(),
+                temp_dir.path().to_path_buf(),
+                PluginSource::Store {
+                    store_id: "store-1".to_string(),
+                },
+            );
+        }
+
+        let err = manager
+            .prepare_dev_watch("com.example.store", &wasm_path)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("only available for local plugins"));
+    }
+
+    #[tokio::test]
+    async fn test_prepare_dev_watch_local_plugin_includes_manifest() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut manager = make_test_manager(temp_dir.path());
+        let source_dir = temp_dir.path().join("dev-plugin");
+        tokio::fs::create_dir_all(&source_dir).await.unwrap();
+        tokio::fs::write(source_dir.join("plugin.toml"), "")
+            .await
+            .unwrap();
+        let wasm_path = source_dir.join("plugin.wasm");
+        tokio::fs::write(&wasm_path, b"\0asm").await.unwrap();
+
+        {
+            let mut reg = manager.registry.write().await;
+            reg.register(
+                make_listener_manifest("com.example.dev", vec![]),
+                wasm_path.clone(),
+                source_dir.clone(),
+                PluginSource::Local {
+                    path: source_dir.display().to_string(),
+                },
+            );
+        }
+
+        let missing = manager
+            .prepare_dev_watch("com.example.dev", &source_dir.join("missing.wasm"))
+            .await;
+        assert!(missing.is_err());
+
+        let info = manager
+            .prepare_dev_watch("com.example.dev", &wasm_path)
+            .await
+            .unwrap();
+        assert_eq!(info.plugin_id, "com.example.dev");
+        assert_eq!(
+            info.manifest_path,
+            Some(source_dir.join("plugin.toml").display().to_string())
+        );
+        assert!(manager.list_dev_watches().is_empty());
+        assert!(!manager.stop_dev_watch("com.example.dev"));
+        assert_eq!(manager.stop_all_dev_watches(), 0);
+    }
+
     #[tokio::test]
     async fn test_dispatch_event_listener_load_failure_isolated() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -3336,6 +4088,108 @@ async fn test_strict_capability_handshake_allows_declared_capability() {
         assert_eq!(records.len(), 1);
         assert!(records[0].allowed);
     }
+
+    #[tokio::test]
+    async fn test_plugin_setting_write_validates_against_schema() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let schema: Vec<crate::plugin::manifest::SettingDeclaration> =
+            serde_json::from_value(serde_json::json!([
+                { "id": "port", "type": "integer", "labelEn": "Port", "default": 8080 }
+            ]))
+            .unwrap();
+
+        let err = validate_plugin_setting(&schema, "port", &serde_json::json!("abc"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("expects type 'integer'"));
+        let err = validate_plugin_setting(&schema, "host", &serde_json::json!("x"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unknown setting 'host'"));
+
+        let value = validate_plugin_setting(&schema, "port", &serde_json::json!("9090")).unwrap();
+        write_plugin_setting(temp_dir.path(), "port", value)
+            .await
+            .unwrap();
+        write_plugin_setting(temp_dir.path(), "legacy", serde_json::json!(1))
+            .await
+            .unwrap();
+
+        let stored = read_plugin_settings_file(temp_dir.path()).await.unwrap();
+        assert_eq!(stored["port"], serde_json::json!(9090));
+        let resolved = crate::plugin::manifest::resolve_setting_values(&schema, &stored);
+        assert_eq!(resolved.values["port"], serde_json::json!(9090));
+        assert_eq!(resolved.unknown_keys, vec!["legacy".to_string()]);
+    }
+}
+
+/// Validate a plugin setting write against the declared schema.
+///
+/// Plugins without a `[[settings]]` schema keep accepting arbitrary keys.
+pub(crate) fn validate_plugin_setting(
+    schema: &[crate::plugin::manifest::SettingDeclaration],
+    key: &str,
+    value: &serde_json::Value,
+) -> CogniaResult<serde_json::Value> {
+    if schema.is_empty() {
+        return Ok(value.clone());
+    }
+    let decl = schema.iter().find(|decl| decl.id == key).ok_or_else(|| {
+        CogniaError::Plugin(format!(
+            "Unknown setting '{}' (declared settings: {})",
+            key,
+            schema
+                .iter()
+                .map(|decl| decl.id.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    })?;
+    decl.validate_value(value).map_err(CogniaError::Plugin)
+}
+
+/// Read `{data_dir}/settings.json`, returning an empty map when absent
+pub(crate) async fn read_plugin_settings_file(
+    data_dir: &Path,
+) -> CogniaResult<HashMap<String, serde_json::Value>> {
+    let settings_path = data_dir.join("settings.json");
+    if !settings_path.exists() {
+        return Ok(HashMap::new());
+    }
+    let content = tokio::fs::read_to_string(&settings_path)
+        .await
+        .map_err(|e| CogniaError::Plugin(format!("Failed to read settings: {}", e)))?;
+    serde_json::from_str(&content)
+        .map_err(|e| CogniaError::Plugin(format!("Invalid settings JSON: {}", e)))
+}
+
+/// Persist a single key into `{data_dir}/settings.json`, preserving other keys
+pub(crate) async fn write_plugin_setting(
+    data_dir: &Path,
+    key: &str,
+    value: serde_json::Value,
+) -> CogniaResult<()> {
+    let _ = tokio::fs::create_dir_all(data_dir).await;
+    let settings_path = data_dir.join("settings.json");
+
+    let mut settings: HashMap<String, serde_json::Value> = if settings_path.exists() {
+        let content = tokio::fs::read_to_string(&settings_path)
+            .await
+            .unwrap_or_default();
+        serde_json::from_str(&content).unwrap_or_default()
+    } else {
+        HashMap::new()
+    };
+
+    settings.insert(key.to_string(), value);
+
+    let content = serde_json::to_string_pretty(&settings)
+        .map_err(|e| CogniaError::Plugin(format!("Failed to serialize settings: {}", e)))?;
+    tokio::fs::write(&settings_path, content)
+        .await
+        .map_err(|e| CogniaError::Plugin(format!("Failed to write settings: {}", e)))?;
+
+    Ok(())
 }
 
 fn ensure_file_exists(path: &Path, label: &str) -> CogniaResult<()> {
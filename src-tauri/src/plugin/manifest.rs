@@ -11,6 +11,14 @@ pub fn is_supported_log_listen_filter(filter: &str) -> bool {
     SUPPORTED_LOG_LISTEN_FILTERS.contains(&filter)
 }
 
+/// Plugin ids use reverse domain notation: alphanumeric, '.', '-', '_'
+pub fn is_valid_plugin_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '.' || c == '-' || c == '_')
+}
+
 /// Plugin manifest parsed from plugin.toml
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PluginManifest {
@@ -39,7 +47,7 @@ pub struct PluginManifest {
 #[serde(rename_all = "camelCase")]
 pub struct SettingDeclaration {
     pub id: String,
-    /// Type: "string", "number", "boolean", "select"
+    /// Type: "string", "number", "integer", "boolean", "select"
     #[serde(alias = "type", rename = "type")]
     pub setting_type: String,
     #[serde(alias = "label_en")]
@@ -54,7 +62,7 @@ pub struct SettingDeclaration {
     pub default: Option<serde_json::Value>,
     #[serde(default)]
     pub required: bool,
-    /// For "number" type
+    /// For "number" and "integer" types
     #[serde(default)]
     pub min: Option<f64>,
     #[serde(default)]
@@ -74,6 +82,172 @@ pub struct SettingOption {
     pub label_zh: Option<String>,
 }
 
+/// Setting types accepted in `[[settings]]` declarations
+pub const SUPPORTED_SETTING_TYPES: &[&str] = &["string", "number", "integer", "boolean", "select"];
+
+impl SettingDeclaration {
+    /// Validate a value against this declaration, returning the normalized value.
+    ///
+    /// String inputs are coerced for non-string types so values written through
+    /// the legacy string-only `config::set` path keep working.
+    pub fn validate_value(&self, value: &serde_json::Value) -> Result<serde_json::Value, String> {
+        use serde_json::Value;
+
+        let mismatch = |value: &Value| {
+            format!(
+                "Setting '{}' expects type '{}', got {}",
+                self.id,
+                self.setting_type,
+                describe_json_value(value)
+            )
+        };
+
+        let normalized = match self.setting_type.as_str() {
+            "string" => match value {
+                Value::String(_) => value.clone(),
+                _ => return Err(mismatch(value)),
+            },
+            "boolean" => match value {
+                Value::Bool(_) => value.clone(),
+                Value::String(s) => match s.trim().to_ascii_lowercase().as_str() {
+                    "true" => Value::Bool(true),
+                    "false" => Value::Bool(false),
+                    _ => return Err(mismatch(value)),
+                },
+                _ => return Err(mismatch(value)),
+            },
+            "number" | "integer" => {
+                let number = match value {
+                    Value::Number(n) => n.as_f64(),
+                    Value::String(s) => s.trim().parse::<f64>().ok(),
+                    _ => None,
+                }
+                .filter(|n| n.is_finite())
+                .ok_or_else(|| mismatch(value))?;
+                if self.setting_type == "integer" && number.fract() != 0.0 {
+                    return Err(mismatch(value));
+                }
+                if let Some(min) = self.min {
+                    if number < min {
+                        return Err(format!(
+                            "Setting '{}' must be >= {}, got {}",
+                            self.id, min, number
+                        ));
+                    }
+                }
+                if let Some(max) = self.max {
+                    if number > max {
+                        return Err(format!(
+                            "Setting '{}' must be <= {}, got {}",
+                            self.id, max, number
+                        ));
+                    }
+                }
+                if self.setting_type == "integer" {
+                    Value::from(number as i64)
+                } else {
+                    serde_json::Number::from_f64(number)
+                        .map(Value::Number)
+                        .ok_or_else(|| mismatch(value))?
+                }
+            }
+            "select" => {
+                let choice = value.as_str().ok_or_else(|| mismatch(value))?;
+                if !self.options.iter().any(|opt| opt.value == choice) {
+                    let allowed: Vec<&str> =
+                        self.options.iter().map(|opt| opt.value.as_str()).collect();
+                    return Err(format!(
+                        "Setting '{}' expects one of [{}], got \"{}\"",
+                        self.id,
+                        allowed.join(", "),
+                        choice
+                    ));
+                }
+                value.clone()
+            }
+            other => {
+                return Err(format!(
+                    "Setting '{}' declares unsupported type '{}'",
+                    self.id, other
+                ))
+            }
+        };
+
+        Ok(normalized)
+    }
+}
+
+fn describe_json_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => "null".to_string(),
+        serde_json::Value::Bool(b) => format!("boolean {}", b),
+        serde_json::Value::Number(n) => format!("number {}", n),
+        serde_json::Value::String(s) => format!("string \"{}\"", s),
+        serde_json::Value::Array(_) => "array".to_string(),
+        serde_json::Value::Object(_) => "object".to_string(),
+    }
+}
+
+/// Plugin setting values resolved against the declared schema.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedPluginSettings {
+    /// Declared settings: stored value if valid, otherwise the declared default
+    pub values: std::collections::HashMap<String, serde_json::Value>,
+    /// Declared settings whose value came from the manifest default
+    pub defaulted_keys: Vec<String>,
+    /// Stored keys that are no longer declared (kept on disk, not applied)
+    pub unknown_keys: Vec<String>,
+    /// Stored values that no longer satisfy the schema (default applied instead)
+    pub invalid_keys: Vec<String>,
+    /// Raw stored values for `unknown_keys`
+    pub unknown_values: std::collections::HashMap<String, serde_json::Value>,
+}
+
+/// Merge stored setting values with declared defaults.
+///
+/// Schema changes between plugin versions never drop stored data: undeclared keys
+/// are reported as unknown and values that fail validation fall back to the default.
+pub fn resolve_setting_values(
+    schema: &[SettingDeclaration],
+    stored: &std::collections::HashMap<String, serde_json::Value>,
+) -> ResolvedPluginSettings {
+    let mut resolved = ResolvedPluginSettings::default();
+
+    for decl in schema {
+        match stored.get(&decl.id) {
+            Some(value) => match decl.validate_value(value) {
+                Ok(normalized) => {
+                    resolved.values.insert(decl.id.clone(), normalized);
+                }
+                Err(_) => {
+                    resolved.invalid_keys.push(decl.id.clone());
+                    if let Some(default) = &decl.default {
+                        resolved.values.insert(decl.id.clone(), default.clone());
+                        resolved.defaulted_keys.push(decl.id.clone());
+                    }
+                }
+            },
+            None => {
+                if let Some(default) = &decl.default {
+                    resolved.values.insert(decl.id.clone(), default.clone());
+                    resolved.defaulted_keys.push(decl.id.clone());
+                }
+            }
+        }
+    }
+
+    for (key, value) in stored {
+        if !schema.iter().any(|decl| &decl.id == key) {
+            resolved.unknown_keys.push(key.clone());
+            resolved.unknown_values.insert(key.clone(), value.clone());
+        }
+    }
+    resolved.unknown_keys.sort();
+
+    resolved
+}
+
 /// Dependencies declared by a plugin
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(default, rename_all = "camelCase")]
@@ -117,6 +291,9 @@ pub struct PluginMeta {
     /// Log sources this plugin wants to observe (currently "plugin" or "*")
     #[serde(default, alias = "listen_logs")]
     pub listen_logs: Vec<String>,
+    /// Locale used when neither the app locale nor its language is shipped (defaults to "en")
+    #[serde(default, alias = "default_locale")]
+    pub default_locale: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -343,12 +520,7 @@ fn validate(&self) -> CogniaResult<()> {
             }
         }
         // Validate plugin id format (reverse domain notation)
-        if !self
-            .plugin
-            .id
-            .chars()
-            .all(|c| c.is_alphanumeric() || c == '.' || c == '-' || c == '_')
-        {
+        if !is_valid_plugin_id(&self.plugin.id) {
             return Err(CogniaError::Plugin(format!(
                 "Invalid plugin id '{}': must contain only alphanumeric, '.', '-', '_'",
                 self.plugin.id
@@ -398,6 +570,45 @@ fn validate(&self) -> CogniaResult<()> {
                 _ => {}
             }
         }
+        // Validate settings schema: unique ids, known types, defaults that pass validation
+        let mut seen_settings = HashSet::new();
+        for setting in &self.settings {
+            if setting.id.trim().is_empty() {
+                return Err(CogniaError::Plugin(format!(
+                    "Plugin '{}' declares a setting with an empty id",
+                    self.plugin.id
+                )));
+            }
+            if !seen_settings.insert(setting.id.as_str()) {
+                return Err(CogniaError::Plugin(format!(
+                    "Duplicate setting id '{}' in plugin '{}'",
+                    setting.id, self.plugin.id
+                )));
+            }
+            if !SUPPORTED_SETTING_TYPES.contains(&setting.setting_type.as_str()) {
+                return Err(CogniaError::Plugin(format!(
+                    "Setting '{}' in plugin '{}' has unsupported type '{}' (expected one of: {})",
+                    setting.id,
+                    self.plugin.id,
+                    setting.setting_type,
+                    SUPPORTED_SETTING_TYPES.join(", ")
+                )));
+            }
+            if setting.setting_type == "select" && setting.options.is_empty() {
+                return Err(CogniaError::Plugin(format!(
+                    "Select setting '{}' in plugin '{}' declares no options",
+                    setting.id, self.plugin.id
+                )));
+            }
+            if let Some(default) = &setting.default {
+                setting.validate_value(default).map_err(|e| {
+                    CogniaError::Plugin(format!(
+                        "Invalid default in plugin '{}': {}",
+                        self.plugin.id, e
+                    ))
+                })?;
+            }
+        }
         validate_manifest_plugin_points(self)?;
         Ok(())
     }
@@ -802,6 +1013,20 @@ fn test_listen_logs_accepts_supported_plugin_filter() {
         assert_eq!(manifest.plugin.listen_logs, vec!["plugin"]);
     }
 
+    #[test]
+    fn test_default_locale_parses() {
+        let toml = r#"
+[plugin]
+id = "com.example.i18n"
+name = "I18n"
+version = "1.0.0"
+default_locale = "zh"
+"#;
+
+        let manifest = PluginManifest::from_str(toml).unwrap();
+        assert_eq!(manifest.plugin.default_locale.as_deref(), Some("zh"));
+    }
+
     #[test]
     fn test_listen_logs_rejects_empty_value() {
         let toml = r#"
@@ -856,4 +1081,113 @@ fn test_reject_invalid_tool_contract_version() {
         let err = PluginManifest::from_str(toml).unwrap_err().to_string();
         assert!(err.contains("Invalid tool_contract_version"));
     }
+
+    const SETTINGS_TOML: &str = r#"
+[plugin]
+id = "com.example.settings"
+name = "Settings"
+version = "1.0.0"
+
+[[settings]]
+id = "retries"
+type = "integer"
+label_en = "Retries"
+default = 3
+min = 0
+max = 10
+
+[[settings]]
+id = "verbose"
+type = "boolean"
+label_en = "Verbose"
+default = false
+
+[[settings]]
+id = "mode"
+type = "select"
+label_en = "Mode"
+default = "fast"
+options = [{ value = "fast", labelEn = "Fast" }, { value = "safe", labelEn = "Safe" }]
+"#;
+
+    #[test]
+    fn test_settings_schema_parses_and_validates_values() {
+        let manifest = PluginManifest::from_str(SETTINGS_TOML).unwrap();
+        let retries = &manifest.settings[0];
+        assert_eq!(
+            retries.validate_value(&serde_json::json!("5")).unwrap(),
+            serde_json::json!(5)
+        );
+        let err = retries.validate_value(&serde_json::json!(1.5)).unwrap_err();
+        assert!(err.contains("expects type 'integer'"));
+        let err = retries.validate_value(&serde_json::json!(11)).unwrap_err();
+        assert!(err.contains("must be <= 10"));
+
+        let verbose = &manifest.settings[1];
+        assert_eq!(
+            verbose.validate_value(&serde_json::json!("true")).unwrap(),
+            serde_json::json!(true)
+        );
+
+        let mode = &manifest.settings[2];
+        let err = mode.validate_value(&serde_json::json!("slow")).unwrap_err();
+        assert!(err.contains("expects one of [fast, safe]"));
+    }
+
+    #[test]
+    fn test_settings_schema_rejects_invalid_default() {
+        let toml = r#"
+[plugin]
+id = "com.example.settings"
+name = "Settings"
+version = "1.0.0"
+
+[[settings]]
+id = "retries"
+type = "integer"
+label_en = "Retries"
+default = "many"
+"#;
+        let err = PluginManifest::from_str(toml).unwrap_err().to_string();
+        assert!(err.contains("Invalid default"));
+        assert!(err.contains("expects type 'integer'"));
+    }
+
+    #[test]
+    fn test_settings_schema_rejects_unsupported_type() {
+        let toml = r#"
+[plugin]
+id = "com.example.settings"
+name = "Settings"
+version = "1.0.0"
+
+[[settings]]
+id = "color"
+type = "rgb"
+label_en = "Color"
+"#;
+        let err = PluginManifest::from_str(toml).unwrap_err().to_string();
+        assert!(err.contains("unsupported type 'rgb'"));
+    }
+
+    #[test]
+    fn test_resolve_setting_values_merges_defaults_and_flags_schema_drift() {
+        let manifest = PluginManifest::from_str(SETTINGS_TOML).unwrap();
+        let mut stored = std::collections::HashMap::new();
+        stored.insert("retries".to_string(), serde_json::json!(7));
+        stored.insert("mode".to_string(), serde_json::json!("removed-mode"));
+        stored.insert("legacyFlag".to_string(), serde_json::json!(true));
+
+        let resolved = resolve_setting_values(&manifest.settings, &stored);
+        assert_eq!(resolved.values["retries"], serde_json::json!(7));
+        assert_eq!(resolved.values["verbose"], serde_json::json!(false));
+        assert_eq!(resolved.values["mode"], serde_json::json!("fast"));
+        assert_eq!(resolved.invalid_keys, vec!["mode".to_string()]);
+        assert_eq!(resolved.unknown_keys, vec!["legacyFlag".to_string()]);
+        assert_eq!(
+            resolved.unknown_values["legacyFlag"],
+            serde_json::json!(true)
+        );
+        assert!(!resolved.values.contains_key("legacyFlag"));
+    }
 }
@@ -0,0 +1,407 @@
+//! Remote plugin marketplace index.
+//!
+//! The index is a JSON document listing installable plugins. Publishers wrap it in
+//! an envelope carrying an ed25519 signature over the raw payload string so the
+//! launcher can verify it against the configured trusted keys before trusting any
+//! download URL or checksum it contains.
+
+use crate::error::{CogniaError, CogniaResult};
+use crate::plugin::permissions::PERMISSION_NAMES;
+use ring::signature::{UnparsedPublicKey, ED25519};
+use serde::{Deserialize, Serialize};
+
+/// MetadataCache key holding the raw index envelope.
+pub const MARKETPLACE_INDEX_CACHE_KEY: &str = "plugin:marketplace:index";
+
+/// How long a fetched index is considered fresh.
+pub const MARKETPLACE_INDEX_TTL_SECS: i64 = 6 * 60 * 60;
+
+/// Signed wrapper around the serialized index.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketplaceIndexEnvelope {
+    /// The index JSON exactly as it was signed.
+    pub payload: String,
+    /// Hex-encoded ed25519 signature over `payload`.
+    #[serde(default)]
+    pub signature: Option<String>,
+    /// Optional publisher key identifier (informational).
+    #[serde(default)]
+    pub key_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketplaceIndex {
+    #[serde(default)]
+    pub generated_at: Option<String>,
+    #[serde(default)]
+    pub plugins: Vec<MarketplaceIndexEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketplaceIndexEntry {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    /// Permissions the packaged manifest declares.
+    #[serde(default)]
+    pub permissions: Vec<String>,
+    pub download_url: String,
+    /// sha256 of the downloaded archive.
+    pub sha256: String,
+    #[serde(default)]
+    pub changelog: Option<String>,
+    #[serde(default)]
+    pub homepage: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Cached copy of the last fetched index, keyed to the URL it came from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedMarketplaceIndex {
+    pub url: String,
+    pub envelope: MarketplaceIndexEnvelope,
+    pub fetched_at: String,
+}
+
+/// An index whose signature (if any) has been checked against the trusted keys.
+#[derive(Debug, Clone)]
+pub struct VerifiedMarketplaceIndex {
+    pub index: MarketplaceIndex,
+    pub signed: bool,
+    pub key_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketplaceFilter {
+    /// Case-insensitive match against id, name, description and tags.
+    #[serde(default)]
+    pub query: Option<String>,
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// Only list plugins that do not request this permission.
+    #[serde(default)]
+    pub exclude_permission: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketplaceIndexSummary {
+    pub url: String,
+    pub plugin_count: usize,
+    pub signed: bool,
+    pub key_id: Option<String>,
+    pub generated_at: Option<String>,
+    pub fetched_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketplacePluginListing {
+    #[serde(flatten)]
+    pub entry: MarketplaceIndexEntry,
+    pub installed_version: Option<String>,
+    pub update_available: bool,
+    pub signed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketplaceInstallReport {
+    pub plugin_id: String,
+    pub version: String,
+    pub previous_version: Option<String>,
+    /// Permissions added by this version that were not re-granted and stay revoked.
+    pub pending_permissions: Vec<String>,
+}
+
+/// Accept either a signed envelope or a bare (unsigned) index document.
+pub fn parse_index_document(raw: &str) -> CogniaResult<MarketplaceIndexEnvelope> {
+    let value: serde_json::Value = serde_json::from_str(raw)
+        .map_err(|e| CogniaError::Parse(format!("Invalid marketplace index JSON: {}", e)))?;
+
+    if value
+        .get("payload")
+        .is_some_and(|payload| payload.is_string())
+    {
+        return serde_json::from_value(value)
+            .map_err(|e| CogniaError::Parse(format!("Invalid marketplace index envelope: {}", e)));
+    }
+
+    Ok(MarketplaceIndexEnvelope {
+        payload: raw.to_string(),
+        signature: None,
+        key_id: None,
+    })
+}
+
+fn signature_matches(payload: &[u8], signature: &[u8], public_key_hex: &str) -> bool {
+    let Ok(public_key) = hex::decode(public_key_hex.trim()) else {
+        return false;
+    };
+    UnparsedPublicKey::new(&ED25519, public_key)
+        .verify(payload, signature)
+        .is_ok()
+}
+
+/// Verify the envelope signature and parse the payload.
+///
+/// A present signature must match one of `trusted_keys`; a missing signature
+/// yields an index marked `signed: false` and callers decide whether to accept it.
+pub fn verify_index_envelope(
+    envelope: &MarketplaceIndexEnvelope,
+    trusted_keys: &[String],
+) -> CogniaResult<VerifiedMarketplaceIndex> {
+    let signed = match envelope.signature.as_deref().map(str::trim) {
+        Some(signature) if !signature.is_empty() => {
+            if trusted_keys.is_empty() {
+                return Err(CogniaError::Plugin(
+                    "Marketplace index is signed but no trusted keys are configured \
+                     (plugin.marketplace_trusted_keys)."
+                        .to_string(),
+                ));
+            }
+            let signature = hex::decode(signature).map_err(|_| {
+                CogniaError::Plugin("Marketplace index signature is not valid hex.".to_string())
+            })?;
+            let payload = envelope.payload.as_bytes();
+            if !trusted_keys
+                .iter()
+                .any(|key| signature_matches(payload, &signature, key))
+            {
+                return Err(CogniaError::Plugin(
+                    "Marketplace index signature does not match any trusted key.".to_string(),
+                ));
+            }
+            true
+        }
+        _ => false,
+    };
+
+    let index: MarketplaceIndex = serde_json::from_str(&envelope.payload)
+        .map_err(|e| CogniaError::Parse(format!("Invalid marketplace index payload: {}", e)))?;
+    validate_index(&index)?;
+
+    Ok(VerifiedMarketplaceIndex {
+        index,
+        signed,
+        key_id: envelope.key_id.clone(),
+    })
+}
+
+fn validate_index(index: &MarketplaceIndex) -> CogniaResult<()> {
+    let mut seen = std::collections::HashSet::new();
+    for entry in &index.plugins {
+        if entry.id.trim().is_empty() {
+            return Err(CogniaError::Plugin(
+                "Marketplace index contains an entry without an id.".to_string(),
+            ));
+        }
+        if !seen.insert(entry.id.as_str()) {
+            return Err(CogniaError::Plugin(format!(
+                "Marketplace index lists '{}' more than once.",
+                entry.id
+            )));
+        }
+        if entry.sha256.len() != 64 || !entry.sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(CogniaError::Plugin(format!(
+                "Marketplace entry '{}' has invalid sha256 '{}'.",
+                entry.id, entry.sha256
+            )));
+        }
+        if let Some(unknown) = entry
+            .permissions
+            .iter()
+            .find(|permission| !PERMISSION_NAMES.contains(&permission.as_str()))
+        {
+            return Err(CogniaError::Plugin(format!(
+                "Marketplace entry '{}' lists unknown permission '{}'.",
+                entry.id, unknown
+            )));
+        }
+    }
+    Ok(())
+}
+
+pub fn entry_matches_filter(entry: &MarketplaceIndexEntry, filter: &MarketplaceFilter) -> bool {
+    if let Some(query) = filter
+        .query
+        .as_deref()
+        .map(str::trim)
+        .filter(|query| !query.is_empty())
+    {
+        let query = query.to_lowercase();
+        let matches = entry.id.to_lowercase().contains(&query)
+            || entry.name.to_lowercase().contains(&query)
+            || entry
+                .description
+                .as_deref()
+                .is_some_and(|description| description.to_lowercase().contains(&query))
+            || entry
+                .tags
+                .iter()
+                .any(|tag| tag.to_lowercase().contains(&query));
+        if !matches {
+            return false;
+        }
+    }
+
+    if let Some(tag) = filter.tag.as_deref().filter(|tag| !tag.is_empty()) {
+        if !entry
+            .tags
+            .iter()
+            .any(|candidate| candidate.eq_ignore_ascii_case(tag))
+        {
+            return false;
+        }
+    }
+
+    if let Some(permission) = filter.exclude_permission.as_deref() {
+        if entry.permissions.iter().any(|p| p == permission) {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Permissions present in `next` that `previous` did not declare.
+pub fn permission_escalation(previous: &[String], next: &[String]) -> Vec<String> {
+    let mut added: Vec<String> = next
+        .iter()
+        .filter(|permission| !previous.contains(permission))
+        .cloned()
+        .collect();
+    added.sort();
+    added.dedup();
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    const SEED: [u8; 32] = [7; 32];
+
+    fn sample_payload() -> String {
+        serde_json::json!({
+            "generatedAt": "2026-01-01T00:00:00Z",
+            "plugins": [{
+                "id": "com.example.hello",
+                "name": "Hello",
+                "version": "1.2.0",
+                "description": "Says hello",
+                "permissions": ["config_read", "http"],
+                "downloadUrl": "https://plugins.example.com/hello-1.2.0.zip",
+                "sha256": "a".repeat(64),
+                "tags": ["demo"]
+            }]
+        })
+        .to_string()
+    }
+
+    fn signed_envelope(payload: &str) -> (MarketplaceIndexEnvelope, String) {
+        let key_pair = Ed25519KeyPair::from_seed_unchecked(&SEED).unwrap();
+        let signature = key_pair.sign(payload.as_bytes());
+        (
+            MarketplaceIndexEnvelope {
+                payload: payload.to_string(),
+                signature: Some(hex::encode(signature.as_ref())),
+                key_id: Some("test".into()),
+            },
+            hex::encode(key_pair.public_key().as_ref()),
+        )
+    }
+
+    #[test]
+    fn test_verify_signed_index() {
+        let (envelope, public_key) = signed_envelope(&sample_payload());
+        let verified = verify_index_envelope(&envelope, &[public_key]).unwrap();
+        assert!(verified.signed);
+        assert_eq!(verified.index.plugins.len(), 1);
+        assert_eq!(verified.index.plugins[0].id, "com.example.hello");
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_payload_and_unknown_key() {
+        let (mut envelope, public_key) = signed_envelope(&sample_payload());
+        assert!(verify_index_envelope(&envelope, &["00".repeat(32)]).is_err());
+        assert!(verify_index_envelope(&envelope, &[]).is_err());
+
+        envelope.payload = envelope.payload.replace("1.2.0", "1.3.0");
+        assert!(verify_index_envelope(&envelope, &[public_key]).is_err());
+    }
+
+    #[test]
+    fn test_parse_bare_index_is_unsigned() {
+        let payload = sample_payload();
+        let envelope = parse_index_document(&payload).unwrap();
+        assert!(envelope.signature.is_none());
+        let verified = verify_index_envelope(&envelope, &[]).unwrap();
+        assert!(!verified.signed);
+
+        let wrapped = serde_json::to_string(&signed_envelope(&payload).0).unwrap();
+        assert!(parse_index_document(&wrapped).unwrap().signature.is_some());
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_entries() {
+        let bad_hash = sample_payload().replace(&"a".repeat(64), "abc");
+        assert!(verify_index_envelope(&parse_index_document(&bad_hash).unwrap(), &[]).is_err());
+
+        let bad_permission = sample_payload().replace("config_read", "root_access");
+        assert!(
+            verify_index_envelope(&parse_index_document(&bad_permission).unwrap(), &[]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_filter_and_escalation() {
+        let index: MarketplaceIndex = serde_json::from_str(&sample_payload()).unwrap();
+        let entry = &index.plugins[0];
+        assert!(entry_matches_filter(entry, &MarketplaceFilter::default()));
+        assert!(entry_matches_filter(
+            entry,
+            &MarketplaceFilter {
+                query: Some("HELLO".into()),
+                ..Default::default()
+            }
+        ));
+        assert!(!entry_matches_filter(
+            entry,
+            &MarketplaceFilter {
+                tag: Some("other".into()),
+                ..Default::default()
+            }
+        ));
+        assert!(!entry_matches_filter(
+            entry,
+            &MarketplaceFilter {
+                exclude_permission: Some("http".into()),
+                ..Default::default()
+            }
+        ));
+
+        let previous = vec!["config_read".to_string()];
+        let next = vec![
+            "http".to_string(),
+            "config_read".to_string(),
+            "process_exec".to_string(),
+        ];
+        assert_eq!(
+            permission_escalation(&previous, &next),
+            vec!["http".to_string(), "process_exec".to_string()]
+        );
+        assert!(permission_escalation(&next, &previous).is_empty());
+    }
+}
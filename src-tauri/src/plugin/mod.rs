@@ -1,12 +1,16 @@
 pub mod contract;
+pub mod dev_watch;
 pub mod extension_points;
 pub mod host_functions;
+pub mod i18n;
 pub mod loader;
 pub mod manager;
 pub mod manifest;
+pub mod marketplace;
 pub mod permissions;
 pub mod registry;
 pub mod scaffold;
+pub mod storage;
 
 pub use manager::{PluginDeps, PluginManager, PluginUpdateInfo};
 pub use manifest::PluginManifest;
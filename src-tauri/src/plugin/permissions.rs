@@ -22,6 +22,80 @@ pub fn from_config_value(value: &str) -> Self {
     }
 }
 
+/// Every permission name a manifest can declare.
+pub const PERMISSION_NAMES: &[&str] = &[
+    "config_read",
+    "config_write",
+    "env_read",
+    "pkg_search",
+    "pkg_install",
+    "clipboard",
+    "notification",
+    "ui_feedback",
+    "ui_dialog",
+    "ui_file_picker",
+    "ui_navigation",
+    "process_exec",
+    "fs_read",
+    "fs_write",
+    "http",
+    "download_read",
+    "download_write",
+    "git_read",
+    "git_write",
+    "health_read",
+    "profiles_read",
+    "profiles_write",
+    "cache_read",
+    "cache_write",
+    "shell_read",
+    "wsl_read",
+    "launch",
+];
+
+fn is_permission_declared(declared: &PluginPermissions, permission: &str) -> bool {
+    match permission {
+        "config_read" => declared.config_read,
+        "config_write" => declared.config_write,
+        "env_read" => declared.env_read,
+        "pkg_search" => declared.pkg_search,
+        "pkg_install" => declared.pkg_install,
+        "clipboard" => declared.clipboard,
+        "notification" => declared.notification,
+        "ui_feedback" => declared.ui_feedback,
+        "ui_dialog" => declared.ui_dialog,
+        "ui_file_picker" => declared.ui_file_picker,
+        "ui_navigation" => declared.ui_navigation,
+        "process_exec" => declared.process_exec,
+        "fs_read" => !declared.fs_read.is_empty(),
+        "fs_write" => !declared.fs_write.is_empty(),
+        "http" => !declared.http.is_empty(),
+        // Extended SDK v1.1
+        "download_read" => declared.download_read,
+        "download_write" => declared.download_write,
+        "git_read" => declared.git_read,
+        "git_write" => declared.git_write,
+        "health_read" => declared.health_read,
+        "profiles_read" => declared.profiles_read,
+        "profiles_write" => declared.profiles_write,
+        "cache_read" => declared.cache_read,
+        "cache_write" => declared.cache_write,
+        "shell_read" => declared.shell_read,
+        "wsl_read" => declared.wsl_read,
+        "launch" => declared.launch,
+        _ => false,
+    }
+}
+
+/// Names of all permissions declared by a manifest.
+pub fn declared_permission_names(declared: &PluginPermissions) -> Vec<String> {
+    PERMISSION_NAMES
+        .iter()
+        .filter(|name| is_permission_declared(declared, name))
+        .map(|name| name.to_string())
+        .collect()
+}
+
 /// Runtime permission state for a loaded plugin
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PluginPermissionState {
@@ -159,37 +233,7 @@ pub fn get_state(&self, plugin_id: &str) -> Option<&PluginPermissionState> {
     }
 
     fn is_declared_permission(state: &PluginPermissionState, permission: &str) -> bool {
-        match permission {
-            "config_read" => state.declared.config_read,
-            "config_write" => state.declared.config_write,
-            "env_read" => state.declared.env_read,
-            "pkg_search" => state.declared.pkg_search,
-            "pkg_install" => state.declared.pkg_install,
-            "clipboard" => state.declared.clipboard,
-            "notification" => state.declared.notification,
-            "ui_feedback" => state.declared.ui_feedback,
-            "ui_dialog" => state.declared.ui_dialog,
-            "ui_file_picker" => state.declared.ui_file_picker,
-            "ui_navigation" => state.declared.ui_navigation,
-            "process_exec" => state.declared.process_exec,
-            "fs_read" => !state.declared.fs_read.is_empty(),
-            "fs_write" => !state.declared.fs_write.is_empty(),
-            "http" => !state.declared.http.is_empty(),
-            // Extended SDK v1.1
-            "download_read" => state.declared.download_read,
-            "download_write" => state.declared.download_write,
-            "git_read" => state.declared.git_read,
-            "git_write" => state.declared.git_write,
-            "health_read" => state.declared.health_read,
-            "profiles_read" => state.declared.profiles_read,
-            "profiles_write" => state.declared.profiles_write,
-            "cache_read" => state.declared.cache_read,
-            "cache_write" => state.declared.cache_write,
-            "shell_read" => state.declared.shell_read,
-            "wsl_read" => state.declared.wsl_read,
-            "launch" => state.declared.launch,
-            _ => false,
-        }
+        is_permission_declared(&state.declared, permission)
     }
 
     pub fn grant_permission(&mut self, plugin_id: &str, permission: &str) -> CogniaResult<()> {
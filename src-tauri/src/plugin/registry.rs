@@ -366,6 +366,7 @@ fn make_manifest(id: &str, name: &str, tools: Vec<ToolDeclaration>) -> PluginMan
                 update_url: None,
                 listen_events: vec![],
                 listen_logs: vec![],
+                default_locale: None,
             },
             tools,
             permissions: PluginPermissions::default(),
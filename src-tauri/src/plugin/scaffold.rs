@@ -1,6 +1,7 @@
 use crate::error::{CogniaError, CogniaResult};
 use crate::plugin::contract::TOOL_CONTRACT_VERSION;
 use crate::plugin::extension_points::load_plugin_point_matrix;
+use crate::plugin::manifest::is_valid_plugin_id;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
@@ -35,6 +36,18 @@ pub struct ScaffoldConfig {
     pub extension_points: Vec<String>,
     #[serde(default)]
     pub template_options: ScaffoldTemplateOptions,
+    #[serde(default)]
+    pub template: ScaffoldTemplate,
+    /// Locale codes to generate translation files for; `en` is always included
+    #[serde(default = "default_locales")]
+    pub locales: Vec<String>,
+    /// Emit unit tests for the generated tool functions
+    #[serde(default)]
+    pub with_tests: bool,
+}
+
+fn default_locales() -> Vec<String> {
+    vec!["en".to_string(), "zh".to_string()]
 }
 
 fn default_language() -> PluginLanguage {
@@ -74,6 +87,22 @@ fn default() -> Self {
     }
 }
 
+/// Project template the scaffolder starts from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ScaffoldTemplate {
+    /// Single tool returning text/JSON output
+    Basic,
+    /// Declarative `ui` panel with a table, action buttons and an iframe companion view
+    UiPanel,
+}
+
+impl Default for ScaffoldTemplate {
+    fn default() -> Self {
+        Self::Basic
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum ScaffoldContractTemplate {
@@ -290,31 +319,21 @@ pub async fn scaffold_plugin(config: &ScaffoldConfig) -> CogniaResult<ScaffoldRe
         .await
         .map_err(|e| CogniaError::Plugin(format!("Failed to create locales dir: {}", e)))?;
 
-    let en_json = serde_json::json!({
-        "toolName": format!("{} Tool", config.name),
-        "toolDesc": format!("A tool provided by {}", config.name),
-        "greeting": "Hello from {name}!",
-    });
-    tokio::fs::write(
-        locales_dir.join("en.json"),
-        serde_json::to_string_pretty(&en_json).unwrap(),
-    )
-    .await
-    .map_err(|e| CogniaError::Plugin(format!("Failed to write en.json: {}", e)))?;
-    files_created.push("locales/en.json".to_string());
-
-    let zh_json = serde_json::json!({
-        "toolName": format!("{} 工具", config.name),
-        "toolDesc": format!("由 {} 提供的工具", config.name),
-        "greeting": "你好，来自 {name}！",
-    });
-    tokio::fs::write(
-        locales_dir.join("zh.json"),
-        serde_json::to_string_pretty(&zh_json).unwrap(),
-    )
-    .await
-    .map_err(|e| CogniaError::Plugin(format!("Failed to write zh.json: {}", e)))?;
-    files_created.push("locales/zh.json".to_string());
+    for locale in scaffold_locales(config) {
+        let messages: serde_json::Map<String, serde_json::Value> =
+            scaffold_locale_messages(config, &locale)
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), serde_json::Value::String(value)))
+                .collect();
+        let file_name = format!("{}.json", locale);
+        tokio::fs::write(
+            locales_dir.join(&file_name),
+            serde_json::to_string_pretty(&messages).unwrap(),
+        )
+        .await
+        .map_err(|e| CogniaError::Plugin(format!("Failed to write {}: {}", file_name, e)))?;
+        files_created.push(format!("locales/{}", file_name));
+    }
 
     // Generate .gitignore
     let gitignore = "target/\nnode_modules/\ndist/\n.tools/\n*.wasm\n";
@@ -417,13 +436,255 @@ async fn generate_language_project_files(
     config: &ScaffoldConfig,
     plugin_dir: &Path,
 ) -> CogniaResult<Vec<String>> {
-    match config.language {
-        PluginLanguage::Rust => generate_rust_project(config, plugin_dir).await,
-        PluginLanguage::JavaScript => generate_js_project(config, plugin_dir).await,
-        PluginLanguage::TypeScript => generate_ts_project(config, plugin_dir).await,
+    let mut files = match config.language {
+        PluginLanguage::Rust => generate_rust_project(config, plugin_dir).await?,
+        PluginLanguage::JavaScript => generate_js_project(config, plugin_dir).await?,
+        PluginLanguage::TypeScript => generate_ts_project(config, plugin_dir).await?,
+    };
+    // The ui-panel template writes its own tests alongside the panel module
+    if config.with_tests && matches!(config.template, ScaffoldTemplate::Basic) {
+        files.extend(generate_basic_tests(config, plugin_dir).await?);
     }
+    Ok(files)
 }
 
+/// Write tests that run the basic tool's output against stand-ins for the
+/// host and check that every locale translates its greeting
+async fn generate_basic_tests(
+    config: &ScaffoldConfig,
+    plugin_dir: &Path,
+) -> CogniaResult<Vec<String>> {
+    let tests_dir = plugin_dir.join("tests");
+    tokio::fs::create_dir_all(&tests_dir)
+        .await
+        .map_err(|e| CogniaError::Plugin(format!("Failed to create tests dir: {}", e)))?;
+
+    let declarative = has_extension_point(config, "tool-declarative-ui");
+    let (file_name, template, output_test) = match config.language {
+        PluginLanguage::Rust => (
+            "plugin.rs",
+            RUST_BASIC_TESTS,
+            if declarative {
+                RUST_BASIC_BLOCKS_TEST
+            } else {
+                RUST_BASIC_OUTPUT_TEST
+            },
+        ),
+        PluginLanguage::JavaScript => (
+            "plugin.test.js",
+            JS_BASIC_TESTS,
+            if declarative {
+                JS_BASIC_BLOCKS_TEST
+            } else {
+                JS_BASIC_OUTPUT_TEST
+            },
+        ),
+        PluginLanguage::TypeScript => (
+            "plugin.test.ts",
+            TS_BASIC_TESTS,
+            if declarative {
+                TS_BASIC_BLOCKS_TEST
+            } else {
+                TS_BASIC_OUTPUT_TEST
+            },
+        ),
+    };
+    let content = template
+        .replace("{{output_test}}", output_test)
+        .replace("{{entry}}", &config.id.replace(['.', '-'], "_"));
+    tokio::fs::write(tests_dir.join(file_name), content)
+        .await
+        .map_err(|e| CogniaError::Plugin(format!("Failed to write tests/{}: {}", file_name, e)))?;
+    let mut files = vec![format!("tests/{}", file_name)];
+
+    if matches!(config.language, PluginLanguage::TypeScript) {
+        // Type-checks the sources together with the tests, which need Node's types
+        let tsconfig = serde_json::json!({
+            "extends": "./tsconfig.json",
+            "compilerOptions": {
+                "lib": ["es2020"],
+                "types": ["@extism/js-pdk", "node"]
+            },
+            "include": ["src/**/*.ts", "tests/**/*.ts", "plugin.d.ts", "node_modules/@cognia/plugin-sdk/cognia.d.ts"]
+        });
+        tokio::fs::write(
+            plugin_dir.join("tsconfig.test.json"),
+            serde_json::to_string_pretty(&tsconfig).unwrap(),
+        )
+        .await
+        .map_err(|e| CogniaError::Plugin(format!("Failed to write tsconfig.test.json: {}", e)))?;
+        files.push("tsconfig.test.json".to_string());
+    }
+    Ok(files)
+}
+
+const RUST_BASIC_TESTS: &str = r#"use std::fs;
+
+{{output_test}}
+#[test]
+fn every_locale_translates_the_greeting() {
+    for entry in fs::read_dir("locales").expect("locales/ should exist") {
+        let path = entry.expect("locale entry").path();
+        let content = fs::read_to_string(&path).expect("locale should be readable");
+        let messages: serde_json::Value = serde_json::from_str(&content)
+            .unwrap_or_else(|_| panic!("invalid JSON in {}", path.display()));
+        let greeting = messages["greeting"].as_str().unwrap_or_default();
+        assert!(
+            greeting.contains("{name}"),
+            "{} should translate greeting with a {{name}} placeholder",
+            path.display()
+        );
+    }
+}
+"#;
+
+const RUST_BASIC_OUTPUT_TEST: &str = r#"#[test]
+fn tool_output_reports_greeting_platform_and_input() {
+    let input = "{\"query\":\"ping\"}";
+    let output = {{entry}}::tool_output("Hello, test-host!", "linux", input);
+    assert_eq!(output["greeting"], "Hello, test-host!");
+    assert_eq!(output["platform"], "linux");
+    assert_eq!(output["input"], input);
+}
+"#;
+
+const RUST_BASIC_BLOCKS_TEST: &str = r#"use cognia_plugin_sdk::ui::UiBlock;
+
+#[test]
+fn tool_blocks_show_a_heading_and_the_platform() {
+    let blocks = {{entry}}::tool_blocks("Hello, test-host!", "linux");
+    assert!(matches!(blocks.first(), Some(UiBlock::Heading { .. })));
+    assert!(blocks.iter().any(|block| matches!(
+        block,
+        UiBlock::Text { content, .. } if content == "Hello, test-host! on linux"
+    )));
+}
+"#;
+
+const JS_BASIC_TESTS: &str = r#"// Runs the tool entry against an in-memory stand-in for the Extism host.
+const test = require('node:test');
+const assert = require('node:assert/strict');
+const { readdirSync, readFileSync } = require('node:fs');
+const { join } = require('node:path');
+
+const INPUT = '{"query":"ping"}';
+let output = '';
+
+globalThis.Host = {
+  inputString: () => INPUT,
+  outputString: (text) => {
+    output = text;
+  },
+  getFunctions: () => ({
+    cognia_platform_info: () => JSON.stringify({ os: 'linux', arch: 'x64', hostname: 'test-host' }),
+    cognia_log: () => '',
+  }),
+};
+
+const plugin = require('../src/index.js');
+
+{{output_test}}
+test('every locale translates the greeting', () => {
+  const dir = join(__dirname, '..', 'locales');
+  for (const file of readdirSync(dir)) {
+    const messages = JSON.parse(readFileSync(join(dir, file), 'utf8'));
+    assert.match(messages.greeting ?? '', /\{name\}/, `${file} should translate greeting`);
+  }
+});
+"#;
+
+const JS_BASIC_OUTPUT_TEST: &str = r#"test('entry reports the platform and echoes its input', () => {
+  plugin.{{entry}}();
+  const result = JSON.parse(output);
+  assert.equal(result.platform, 'linux');
+  assert.equal(result.input, INPUT);
+});
+"#;
+
+const JS_BASIC_BLOCKS_TEST: &str = r#"test('entry renders a heading and the platform', () => {
+  plugin.{{entry}}();
+  const { ui } = JSON.parse(output);
+  assert.equal(ui[0].type, 'heading');
+  assert.match(ui[1].content, /linux/);
+});
+"#;
+
+const TS_BASIC_TESTS: &str = r#"// Runs the tool entry against an in-memory stand-in for the Extism host.
+import test from 'node:test';
+import assert from 'node:assert/strict';
+import { readdirSync, readFileSync } from 'node:fs';
+import { join } from 'node:path';
+
+type HostFunction = (offset: bigint) => bigint;
+
+const INPUT = '{"query":"ping"}';
+const memory = new Map<bigint, string>();
+let nextOffset = BigInt(1);
+let output = '';
+
+function store(text: string): bigint {
+  const offset = nextOffset;
+  nextOffset += BigInt(1);
+  memory.set(offset, text);
+  return offset;
+}
+
+function load(offset: bigint): string {
+  return memory.get(offset) ?? '';
+}
+
+const hostFunctions: Record<string, HostFunction> = {
+  cognia_platform_info: () =>
+    store(JSON.stringify({ os: 'linux', arch: 'x64', hostname: 'test-host', osVersion: '6.0' })),
+  cognia_log: () => store(''),
+  cognia_i18n_translate: (offset) => {
+    const request = JSON.parse(load(offset)) as { params?: Record<string, string> };
+    return store(JSON.stringify({ text: `Hello, ${request.params?.name ?? ''}!` }));
+  },
+};
+
+Object.assign(globalThis, {
+  Memory: {
+    fromString: (text: string) => ({ offset: store(text) }),
+    find: (offset: bigint) => ({ readString: () => load(offset) }),
+  },
+  Host: {
+    inputString: () => INPUT,
+    outputString: (text: string) => {
+      output = text;
+    },
+    getFunctions: () => hostFunctions,
+  },
+});
+
+// The plugin exports through module.exports, so load it once the host is in place
+const plugin = require('../src/index') as Record<string, () => number>;
+
+{{output_test}}
+test('every locale translates the greeting', () => {
+  const dir = join(__dirname, '..', 'locales');
+  for (const file of readdirSync(dir)) {
+    const messages = JSON.parse(readFileSync(join(dir, file), 'utf8')) as { greeting?: string };
+    assert.match(messages.greeting ?? '', /\{name\}/, `${file} should translate greeting`);
+  }
+});
+"#;
+
+const TS_BASIC_OUTPUT_TEST: &str = r#"test('entry greets the host and echoes its input', () => {
+  assert.equal(plugin.{{entry}}(), 0);
+  const result = JSON.parse(output) as { greeting: string; platform: string; input: string };
+  assert.equal(result.greeting, 'Hello, test-host!');
+  assert.equal(result.platform, 'linux');
+  assert.equal(result.input, INPUT);
+});
+"#;
+
+const TS_BASIC_BLOCKS_TEST: &str = r#"test('entry renders the platform', () => {
+  assert.equal(plugin.{{entry}}(), 0);
+  assert.match(output, /linux/);
+});
+"#;
+
 async fn generate_template_option_files(
     config: &ScaffoldConfig,
     plugin_dir: &Path,
@@ -600,17 +861,34 @@ fn validate_scaffold_config(config: &ScaffoldConfig) -> CogniaResult<()> {
         ));
     }
 
-    if !config
-        .id
-        .chars()
-        .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_')
-    {
+    if !is_valid_plugin_id(&config.id) || !config.id.is_ascii() {
         return Err(CogniaError::Plugin(format!(
             "Invalid plugin id '{}': only alphanumeric, '.', '-', '_' are allowed",
             config.id
         )));
     }
 
+    if config.id.split('.').any(|segment| segment.is_empty()) {
+        return Err(CogniaError::Plugin(format!(
+            "Invalid plugin id '{}': '.'-separated segments must not be empty",
+            config.id
+        )));
+    }
+
+    for locale in &config.locales {
+        let trimmed = locale.trim();
+        if trimmed.is_empty()
+            || !trimmed
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(CogniaError::Plugin(format!(
+                "Invalid scaffold locale '{}': use codes such as 'en', 'zh' or 'pt-BR'",
+                locale
+            )));
+        }
+    }
+
     validate_optional_url("repository", config.repository.as_deref())?;
     validate_optional_url("homepage", config.homepage.as_deref())?;
 
@@ -652,6 +930,23 @@ fn validate_scaffold_config(config: &ScaffoldConfig) -> CogniaResult<()> {
         ));
     }
 
+    if matches!(config.template, ScaffoldTemplate::UiPanel) {
+        if !matches!(config.language, PluginLanguage::Rust) {
+            return Err(CogniaError::Plugin(
+                "The ui-panel template currently supports Rust only".to_string(),
+            ));
+        }
+        if let Some(point) = extension_points
+            .iter()
+            .find(|point| is_tool_extension_point(point) && *point != "tool-declarative-ui")
+        {
+            return Err(CogniaError::Plugin(format!(
+                "The ui-panel template renders a declarative tool and cannot use the '{}' extension point",
+                point
+            )));
+        }
+    }
+
     if matches!(config.language, PluginLanguage::JavaScript)
         && extension_points.iter().any(|point| point != "tool-text")
     {
@@ -713,7 +1008,11 @@ fn builtin_framework_dir(language: &PluginLanguage) -> &'static str {
 }
 
 fn build_scaffold_handoff(config: &ScaffoldConfig, plugin_dir: &Path) -> ScaffoldHandoff {
-    let build_commands = build_commands_for_language(&config.language);
+    let mut build_commands = build_commands_for_language(&config.language);
+    if matches!(config.template, ScaffoldTemplate::UiPanel) {
+        build_commands.push("pnpm --dir ui install".to_string());
+        build_commands.push("pnpm --dir ui build".to_string());
+    }
     let artifact_path = plugin_dir.join("plugin.wasm").display().to_string();
     let lifecycle_manifest_path = plugin_dir
         .join("cognia.scaffold.json")
@@ -729,6 +1028,12 @@ fn build_scaffold_handoff(config: &ScaffoldConfig, plugin_dir: &Path) -> Scaffol
                 .to_string(),
         );
     }
+    if matches!(config.template, ScaffoldTemplate::UiPanel) {
+        extension_point_steps.push(
+            "Replace the sample rows in src/panel.rs and keep [locales.*] keys aligned with locales/*.json."
+                .to_string(),
+        );
+    }
     if has_extension_point(config, "event-listener") {
         extension_point_steps.push(
             "Review the generated cognia_on_event callback stub and align listen_events with real host events."
@@ -854,6 +1159,7 @@ fn generate_lifecycle_manifest(config: &ScaffoldConfig, handoff: &ScaffoldHandof
         "schemaVersion": 1,
         "pluginId": config.id.clone(),
         "language": config.language,
+        "template": config.template,
         "profile": handoff.profile,
         "artifactPath": "plugin.wasm",
         "buildCommands": handoff.build_commands.clone(),
@@ -969,6 +1275,78 @@ fn generate_builtin_catalog_entry_sample(
     entry
 }
 
+/// Locale codes to scaffold, `en` first and without duplicates
+fn scaffold_locales(config: &ScaffoldConfig) -> Vec<String> {
+    let mut locales = vec!["en".to_string()];
+    for locale in &config.locales {
+        let trimmed = locale.trim();
+        if !trimmed.is_empty() && !locales.iter().any(|existing| existing == trimmed) {
+            locales.push(trimmed.to_string());
+        }
+    }
+    locales
+}
+
+/// Translation strings for a locale; locales without built-in text start from English
+fn scaffold_locale_messages(config: &ScaffoldConfig, locale: &str) -> Vec<(&'static str, String)> {
+    let name = config.name.trim();
+    let zh = locale == "zh" || locale.starts_with("zh-") || locale.starts_with("zh_");
+    let mut messages = if zh {
+        vec![
+            ("toolName", format!("{} 工具", name)),
+            ("toolDesc", format!("由 {} 提供的工具", name)),
+            ("greeting", "你好，来自 {name}！".to_string()),
+        ]
+    } else {
+        vec![
+            ("toolName", format!("{} Tool", name)),
+            ("toolDesc", format!("A tool provided by {}", name)),
+            ("greeting", "Hello from {name}!".to_string()),
+        ]
+    };
+
+    if matches!(config.template, ScaffoldTemplate::UiPanel) {
+        let panel: [(&'static str, &str); 9] = if zh {
+            [
+                ("panelTitle", name),
+                ("panelDescription", "使用下方按钮管理表格中的条目。"),
+                ("columnName", "名称"),
+                ("columnValue", "值"),
+                ("addItem", "添加条目"),
+                ("clearItems", "清空"),
+                ("refresh", "刷新"),
+                ("emptyState", "暂无条目，点击“添加条目”创建。"),
+                ("itemCount", "共 {count} 个条目"),
+            ]
+        } else {
+            [
+                ("panelTitle", name),
+                (
+                    "panelDescription",
+                    "Manage the rows below with the action buttons.",
+                ),
+                ("columnName", "Name"),
+                ("columnValue", "Value"),
+                ("addItem", "Add item"),
+                ("clearItems", "Clear"),
+                ("refresh", "Refresh"),
+                (
+                    "emptyState",
+                    "No items yet. Click \"Add item\" to create one.",
+                ),
+                ("itemCount", "{count} items"),
+            ]
+        };
+        messages.extend(
+            panel
+                .into_iter()
+                .map(|(key, value)| (key, value.to_string())),
+        );
+    }
+
+    messages
+}
+
 fn validate_optional_url(field: &str, value: Option<&str>) -> CogniaResult<()> {
     if let Some(url) = value.map(|v| v.trim()).filter(|v| !v.is_empty()) {
         if !url.starts_with("http://") && !url.starts_with("https://") {
@@ -1062,6 +1440,12 @@ fn generate_manifest(config: &ScaffoldConfig) -> String {
     let capabilities_line = build_tool_capabilities_line(config);
     let settings_block = build_settings_block(config);
     let ui_block = build_ui_block(config);
+    let companion_tool_block = build_companion_tool_block(config, &tool_id, &entry_fn);
+    let locales_block = build_locales_block(config);
+    let icon = match config.template {
+        ScaffoldTemplate::Basic => "Wrench",
+        ScaffoldTemplate::UiPanel => "LayoutDashboard",
+    };
 
     format!(
         r#"[plugin]
@@ -1080,10 +1464,10 @@ fn generate_manifest(config: &ScaffoldConfig) -> String {
 description_zh = "{desc}"
 category = "developer"
 keywords = [{keywords}]
-icon = "Wrench"
+icon = "{icon}"
 entry = "{entry}"
-{ui_mode_line}{capabilities_line}
-{settings_block}{ui_block}
+{ui_mode_line}{capabilities_line}{companion_tool_block}
+{settings_block}{ui_block}{locales_block}
 
 [permissions]
 {perms}
@@ -1098,11 +1482,14 @@ fn generate_manifest(config: &ScaffoldConfig) -> String {
         listen_logs_line = listen_logs_line,
         tool_id = escape_toml_string(&tool_id),
         keywords = keywords,
+        icon = icon,
         entry = entry_fn,
         ui_mode_line = ui_mode_line,
         capabilities_line = capabilities_line,
+        companion_tool_block = companion_tool_block,
         settings_block = settings_block,
         ui_block = ui_block,
+        locales_block = locales_block,
         perms = perms.join("\n"),
     )
 }
@@ -1164,6 +1551,10 @@ fn is_tool_extension_point(point_id: &str) -> bool {
 }
 
 fn default_tool_extension_point(config: &ScaffoldConfig) -> &'static str {
+    if matches!(config.template, ScaffoldTemplate::UiPanel) {
+        return "tool-declarative-ui";
+    }
+
     if config.template_options.include_unified_contract_samples
         && matches!(
             config.template_options.contract_template,
@@ -1238,7 +1629,9 @@ fn build_settings_block(config: &ScaffoldConfig) -> String {
 }
 
 fn build_ui_block(config: &ScaffoldConfig) -> String {
-    if !has_extension_point(config, "tool-iframe-ui") {
+    if !has_extension_point(config, "tool-iframe-ui")
+        && !matches!(config.template, ScaffoldTemplate::UiPanel)
+    {
         return String::new();
     }
 
@@ -1253,6 +1646,52 @@ fn build_ui_block(config: &ScaffoldConfig) -> String {
     .to_string()
 }
 
+/// The ui-panel template also ships an iframe view of the same panel state,
+/// served through the `[ui]` assets
+fn build_companion_tool_block(config: &ScaffoldConfig, tool_id: &str, entry_fn: &str) -> String {
+    if !matches!(config.template, ScaffoldTemplate::UiPanel) {
+        return String::new();
+    }
+
+    format!(
+        r#"
+[[tools]]
+id = "{tool_id}-view"
+name_en = "{name} View"
+name_zh = "{name} 视图"
+description_en = "{desc}"
+description_zh = "{desc}"
+category = "developer"
+keywords = ["{tool_id}", "ui"]
+icon = "PanelsTopLeft"
+entry = "{entry}_view"
+ui_mode = "iframe"
+"#,
+        tool_id = escape_toml_string(tool_id),
+        name = escape_toml_string(&config.name),
+        desc = escape_toml_string(&config.description),
+        entry = entry_fn,
+    )
+}
+
+fn build_locales_block(config: &ScaffoldConfig) -> String {
+    if !matches!(config.template, ScaffoldTemplate::UiPanel) {
+        return String::new();
+    }
+
+    let mut block = String::new();
+    for locale in scaffold_locales(config) {
+        block.push_str(&format!(
+            "\n\n[locales.\"{}\"]",
+            escape_toml_string(&locale)
+        ));
+        for (key, value) in scaffold_locale_messages(config, &locale) {
+            block.push_str(&format!("\n{} = \"{}\"", key, escape_toml_string(&value)));
+        }
+    }
+    block
+}
+
 fn infer_capability_declarations(config: &ScaffoldConfig) -> Vec<String> {
     let mut caps = Vec::new();
     if config.permissions.ui_feedback {
@@ -1431,6 +1870,20 @@ fn generate_ci_workflow(config: &ScaffoldConfig) -> String {
         }
     };
 
+    let ui_step = if matches!(config.template, ScaffoldTemplate::UiPanel) {
+        r#"
+      - name: Setup pnpm
+        uses: pnpm/action-setup@v4
+        with:
+          version: 9
+      - name: Build iframe view
+        run: |
+          pnpm --dir ui install
+          pnpm --dir ui build"#
+    } else {
+        ""
+    };
+
     format!(
         r#"name: plugin-ci
 
@@ -1443,7 +1896,7 @@ fn generate_ci_workflow(config: &ScaffoldConfig) -> String {
     runs-on: ubuntu-latest
     steps:
       - uses: actions/checkout@v4
-{build_step}
+{build_step}{ui_step}
 "#
     )
 }
@@ -1909,7 +2362,7 @@ async fn generate_rust_project(
 edition = "2021"
 
 [lib]
-crate-type = ["cdylib"]
+crate-type = [{}]
 
 [dependencies]
 cognia-plugin-sdk = {{ git = "https://github.com/AstroAir/CogniaLauncher", path = "plugin-sdk" }}
@@ -1918,6 +2371,12 @@ async fn generate_rust_project(
 serde_json = "1"
 "#,
         config.id.replace('.', "-"),
+        // Integration tests under tests/ link against the rlib on the host target
+        if config.with_tests {
+            "\"cdylib\", \"rlib\""
+        } else {
+            "\"cdylib\""
+        },
     );
     tokio::fs::write(plugin_dir.join("Cargo.toml"), &cargo_toml)
         .await
@@ -1949,24 +2408,43 @@ async fn generate_rust_project(
     } else {
         ""
     };
-    let main_body = if has_extension_point(config, "tool-declarative-ui") {
-        format!(
+    // The tool's result is built by a plain function without host calls, so
+    // the generated tests can exercise it on the native target
+    let (main_body, output_fn) = if has_extension_point(config, "tool-declarative-ui") {
+        (
             r#"    // Render declarative UI blocks for the scaffolded tool
-    let blocks = vec![
+    Ok(cognia::ui::render(&tool_blocks(&greeting, &platform.os)))"#
+                .to_string(),
+            format!(
+                r#"
+/// Declarative UI blocks rendered by the tool
+pub fn tool_blocks(greeting: &str, os: &str) -> Vec<cognia_plugin_sdk::ui::UiBlock> {{
+    vec![
         cognia::ui::heading("{name} Dashboard", 2),
-        cognia::ui::text(&format!("{{}} on {{}}", greeting, platform.os), None),
-    ];
-    Ok(cognia::ui::render(&blocks))"#,
-            name = config.name,
+        cognia::ui::text(&format!("{{}} on {{}}", greeting, os), None),
+    ]
+}}
+"#,
+                name = config.name,
+            ),
         )
     } else {
-        r#"    // Return JSON result
-    Ok(serde_json::json!({
+        (
+            r#"    // Return JSON result
+    Ok(tool_output(&greeting, &platform.os, &input).to_string())"#
+                .to_string(),
+            r#"
+/// JSON result returned by the tool
+pub fn tool_output(greeting: &str, os: &str, input: &str) -> serde_json::Value {
+    serde_json::json!({
         "greeting": greeting,
-        "platform": platform.os,
+        "platform": os,
         "input": input,
-    }).to_string())"#
-            .to_string()
+    })
+}
+"#
+            .to_string(),
+        )
     };
     let event_listener_stub = if has_extension_point(config, "event-listener") {
         r#"
@@ -2011,8 +2489,15 @@ pub fn cognia_on_log(input: String) -> FnResult<String> {
     } else {
         ""
     };
-    let lib_rs = format!(
-        r#"use cognia_plugin_sdk::prelude::*;
+    let lib_rs = match config.template {
+        ScaffoldTemplate::UiPanel => format!(
+            "{}{}{}",
+            RUST_UI_PANEL_LIB_TEMPLATE.replace("{{entry}}", &entry_fn),
+            event_listener_stub,
+            log_listener_stub
+        ),
+        ScaffoldTemplate::Basic => format!(
+            r#"use cognia_plugin_sdk::prelude::*;
 
 #[plugin_fn]
 pub fn {entry}(input: String) -> FnResult<String> {{
@@ -2024,18 +2509,24 @@ pub fn {entry}(input: String) -> FnResult<String> {{
     let greeting = cognia::i18n::translate("greeting", &[("name", &platform.hostname)])?;
 {settings_comment}{main_body}
 }}
-{event_listener_stub}{log_listener_stub}"#,
-        entry = entry_fn,
-        settings_comment = settings_comment,
-        main_body = main_body,
-        event_listener_stub = event_listener_stub,
-        log_listener_stub = log_listener_stub,
-    );
+{output_fn}{event_listener_stub}{log_listener_stub}"#,
+            entry = entry_fn,
+            settings_comment = settings_comment,
+            main_body = main_body,
+            output_fn = output_fn,
+            event_listener_stub = event_listener_stub,
+            log_listener_stub = log_listener_stub,
+        ),
+    };
     tokio::fs::write(src_dir.join("lib.rs"), &lib_rs)
         .await
         .map_err(|e| CogniaError::Plugin(format!("Failed to write src/lib.rs: {}", e)))?;
     files.push("src/lib.rs".to_string());
 
+    if matches!(config.template, ScaffoldTemplate::UiPanel) {
+        files.extend(generate_rust_ui_panel_files(config, plugin_dir).await?);
+    }
+
     if has_extension_point(config, "tool-iframe-ui") {
         let ui_dir = plugin_dir.join("ui");
         tokio::fs::create_dir_all(&ui_dir)
@@ -2056,6 +2547,501 @@ pub fn {entry}(input: String) -> FnResult<String> {{
     Ok(files)
 }
 
+/// Write the panel module, iframe assets and optional unit tests for the ui-panel template
+async fn generate_rust_ui_panel_files(
+    config: &ScaffoldConfig,
+    plugin_dir: &Path,
+) -> CogniaResult<Vec<String>> {
+    let mut files = Vec::new();
+    let entry_fn = config.id.replace(['.', '-'], "_");
+
+    tokio::fs::write(
+        plugin_dir.join("src").join("panel.rs"),
+        RUST_UI_PANEL_MODULE,
+    )
+    .await
+    .map_err(|e| CogniaError::Plugin(format!("Failed to write src/panel.rs: {}", e)))?;
+    files.push("src/panel.rs".to_string());
+
+    let ui_dir = plugin_dir.join("ui");
+    tokio::fs::create_dir_all(&ui_dir)
+        .await
+        .map_err(|e| CogniaError::Plugin(format!("Failed to create ui dir: {}", e)))?;
+    tokio::fs::create_dir_all(ui_dir.join("src"))
+        .await
+        .map_err(|e| CogniaError::Plugin(format!("Failed to create ui/src dir: {}", e)))?;
+    // The view is written in TypeScript and compiled next to index.html
+    let ui_package = serde_json::json!({
+        "name": format!("{}-ui", config.id.replace('.', "-")),
+        "private": true,
+        "scripts": {
+            "build": "tsc -p .",
+            "typecheck": "tsc -p . --noEmit"
+        },
+        "devDependencies": {
+            "typescript": "^5.4.0"
+        }
+    });
+    let ui_tsconfig = serde_json::json!({
+        "compilerOptions": {
+            "target": "es2020",
+            "lib": ["es2020", "dom", "dom.iterable"],
+            "strict": true,
+            "rootDir": "src",
+            "outDir": "."
+        },
+        "include": ["src/**/*.ts"]
+    });
+    let assets = [
+        (
+            "index.html",
+            UI_PANEL_INDEX_HTML.replace("{{name}}", &html_escape(config.name.trim())),
+        ),
+        ("panel.css", UI_PANEL_CSS.to_string()),
+        ("src/panel.ts", UI_PANEL_TS.replace("{{entry}}", &entry_fn)),
+        (
+            "package.json",
+            serde_json::to_string_pretty(&ui_package).unwrap(),
+        ),
+        (
+            "tsconfig.json",
+            serde_json::to_string_pretty(&ui_tsconfig).unwrap(),
+        ),
+    ];
+    for (file_name, content) in assets {
+        tokio::fs::write(ui_dir.join(file_name), content)
+            .await
+            .map_err(|e| CogniaError::Plugin(format!("Failed to write ui/{}: {}", file_name, e)))?;
+        files.push(format!("ui/{}", file_name));
+    }
+
+    if config.with_tests {
+        let tests_dir = plugin_dir.join("tests");
+        tokio::fs::create_dir_all(&tests_dir)
+            .await
+            .map_err(|e| CogniaError::Plugin(format!("Failed to create tests dir: {}", e)))?;
+        tokio::fs::write(
+            tests_dir.join("panel.rs"),
+            RUST_UI_PANEL_TESTS.replace("{{crate}}", &entry_fn),
+        )
+        .await
+        .map_err(|e| CogniaError::Plugin(format!("Failed to write tests/panel.rs: {}", e)))?;
+        files.push("tests/panel.rs".to_string());
+    }
+
+    Ok(files)
+}
+
+fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const RUST_UI_PANEL_LIB_TEMPLATE: &str = r#"use cognia_plugin_sdk::prelude::*;
+
+pub mod panel;
+
+use panel::PanelLabels;
+
+fn load_labels() -> FnResult<PanelLabels> {
+    Ok(PanelLabels::resolve(|key| cognia::i18n::translate(key, &[]))?)
+}
+
+/// Declarative panel rendered natively by the host
+#[plugin_fn]
+pub fn {{entry}}(input: String) -> FnResult<String> {
+    let state = panel::next_state(&input);
+    let labels = load_labels()?;
+    cognia::log::info(&format!("Rendering panel with {} items", state.items.len()))?;
+
+    let blocks = panel::render_panel(&state, &labels);
+    Ok(cognia::ui::render_with_state(
+        &blocks,
+        &serde_json::to_value(&state)?,
+    ))
+}
+
+/// Data source for the iframe view under ui/
+#[plugin_fn]
+pub fn {{entry}}_view(input: String) -> FnResult<String> {
+    let state = panel::next_state(&input);
+    let labels = load_labels()?;
+    Ok(panel::view_payload(&state, &labels).to_string())
+}
+"#;
+
+const RUST_UI_PANEL_MODULE: &str = r#"//! Panel state, actions and rendering.
+//!
+//! Nothing in this module calls the host, so it can be unit tested on the
+//! native target; `lib.rs` wires it to the plugin entry points.
+
+use cognia_plugin_sdk::ui::{self, UiAction, UiBlock};
+use serde::{Deserialize, Serialize};
+
+pub const REFRESH_BUTTON: &str = "refresh";
+pub const ADD_BUTTON: &str = "add-item";
+pub const CLEAR_BUTTON: &str = "clear-items";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PanelItem {
+    pub name: String,
+    pub value: String,
+}
+
+/// State echoed back by the host with every UI action
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PanelState {
+    pub items: Vec<PanelItem>,
+    pub refreshed: u32,
+}
+
+impl PanelState {
+    pub fn from_action(action: Option<&UiAction>) -> Self {
+        action
+            .and_then(|action| action.state.clone())
+            .and_then(|state| serde_json::from_value(state).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Localized strings, keyed like plugin.toml `[locales.*]` and locales/*.json
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PanelLabels {
+    pub panel_title: String,
+    pub panel_description: String,
+    pub column_name: String,
+    pub column_value: String,
+    pub add_item: String,
+    pub clear_items: String,
+    pub refresh: String,
+    pub empty_state: String,
+    pub item_count: String,
+}
+
+impl PanelLabels {
+    pub fn resolve<E>(mut translate: impl FnMut(&str) -> Result<String, E>) -> Result<Self, E> {
+        Ok(Self {
+            panel_title: translate("panelTitle")?,
+            panel_description: translate("panelDescription")?,
+            column_name: translate("columnName")?,
+            column_value: translate("columnValue")?,
+            add_item: translate("addItem")?,
+            clear_items: translate("clearItems")?,
+            refresh: translate("refresh")?,
+            empty_state: translate("emptyState")?,
+            item_count: translate("itemCount")?,
+        })
+    }
+}
+
+/// Apply a button click to the panel state
+pub fn apply_action(mut state: PanelState, action: Option<&UiAction>) -> PanelState {
+    let button_id = match action {
+        Some(action) if action.action == "button_click" => action.button_id.as_deref(),
+        _ => None,
+    };
+    match button_id {
+        Some(ADD_BUTTON) => {
+            let next = state.items.len() + 1;
+            state.items.push(PanelItem {
+                name: format!("item-{}", next),
+                value: next.to_string(),
+            });
+        }
+        Some(CLEAR_BUTTON) => state.items.clear(),
+        Some(REFRESH_BUTTON) => state.refreshed += 1,
+        _ => {}
+    }
+    state
+}
+
+/// Restore the previous state from a tool input and apply its action
+pub fn next_state(input: &str) -> PanelState {
+    let action = ui::parse_action(input);
+    apply_action(PanelState::from_action(action.as_ref()), action.as_ref())
+}
+
+pub fn table_rows(state: &PanelState) -> Vec<Vec<String>> {
+    state
+        .items
+        .iter()
+        .map(|item| vec![item.name.clone(), item.value.clone()])
+        .collect()
+}
+
+pub fn render_panel(state: &PanelState, labels: &PanelLabels) -> Vec<UiBlock> {
+    let count = labels
+        .item_count
+        .replace("{count}", &state.items.len().to_string());
+    let mut blocks = vec![
+        ui::heading(&labels.panel_title, 2),
+        ui::text(&labels.panel_description, Some("muted")),
+        ui::badge(&count, Some("secondary")),
+        ui::divider(),
+    ];
+
+    if state.items.is_empty() {
+        blocks.push(ui::alert(&labels.empty_state, None, None));
+    } else {
+        blocks.push(ui::table(
+            &[labels.column_name.as_str(), labels.column_value.as_str()],
+            &table_rows(state),
+        ));
+    }
+
+    blocks.push(ui::actions(&[
+        ui::button(REFRESH_BUTTON, &labels.refresh, Some("outline"), Some("RefreshCw")),
+        ui::button(ADD_BUTTON, &labels.add_item, None, Some("Plus")),
+        ui::button(CLEAR_BUTTON, &labels.clear_items, Some("destructive"), Some("Trash2")),
+    ]));
+    blocks
+}
+
+/// Payload consumed by ui/src/panel.ts
+pub fn view_payload(state: &PanelState, labels: &PanelLabels) -> serde_json::Value {
+    serde_json::json!({
+        "state": state,
+        "rows": table_rows(state),
+        "labels": labels,
+    })
+}
+"#;
+
+const RUST_UI_PANEL_TESTS: &str = r#"use cognia_plugin_sdk::ui::{UiAction, UiBlock};
+use {{crate}}::panel::{
+    apply_action, next_state, render_panel, view_payload, PanelLabels, PanelState, ADD_BUTTON,
+    CLEAR_BUTTON, REFRESH_BUTTON,
+};
+
+fn labels() -> PanelLabels {
+    PanelLabels::resolve(|key| Ok::<_, ()>(key.to_string())).unwrap()
+}
+
+fn click(button_id: &str, state: &PanelState) -> UiAction {
+    serde_json::from_value(serde_json::json!({
+        "action": "button_click",
+        "buttonId": button_id,
+        "state": state,
+    }))
+    .expect("valid UiAction")
+}
+
+#[test]
+fn add_button_appends_a_row() {
+    let state = apply_action(PanelState::default(), Some(&click(ADD_BUTTON, &PanelState::default())));
+    assert_eq!(state.items.len(), 1);
+    assert_eq!(state.items[0].name, "item-1");
+}
+
+#[test]
+fn clear_button_removes_all_rows() {
+    let mut state = PanelState::default();
+    for _ in 0..3 {
+        state = apply_action(state.clone(), Some(&click(ADD_BUTTON, &state)));
+    }
+    let cleared = apply_action(state.clone(), Some(&click(CLEAR_BUTTON, &state)));
+    assert!(cleared.items.is_empty());
+}
+
+#[test]
+fn state_round_trips_through_tool_input() {
+    let first = next_state(&serde_json::to_string(&click(ADD_BUTTON, &PanelState::default())).unwrap());
+    let second = next_state(&serde_json::to_string(&click(REFRESH_BUTTON, &first)).unwrap());
+    assert_eq!(second.items, first.items);
+    assert_eq!(second.refreshed, 1);
+}
+
+#[test]
+fn render_shows_empty_state_then_table() {
+    let empty = render_panel(&PanelState::default(), &labels());
+    assert!(empty.iter().any(|block| matches!(block, UiBlock::Alert { .. })));
+    assert!(!empty.iter().any(|block| matches!(block, UiBlock::Table { .. })));
+
+    let state = apply_action(PanelState::default(), Some(&click(ADD_BUTTON, &PanelState::default())));
+    let blocks = render_panel(&state, &labels());
+    assert!(blocks.iter().any(|block| matches!(
+        block,
+        UiBlock::Table { rows, .. } if rows.len() == 1
+    )));
+    assert!(blocks.iter().any(|block| matches!(block, UiBlock::Actions { buttons } if buttons.len() == 3)));
+}
+
+#[test]
+fn view_payload_exposes_rows_and_labels() {
+    let state = apply_action(PanelState::default(), Some(&click(ADD_BUTTON, &PanelState::default())));
+    let payload = view_payload(&state, &labels());
+    assert_eq!(payload["rows"][0][0], "item-1");
+    assert_eq!(payload["labels"]["addItem"], "addItem");
+}
+"#;
+
+const UI_PANEL_INDEX_HTML: &str = r#"<!doctype html>
+<html>
+  <head>
+    <meta charset="utf-8">
+    <title>{{name}}</title>
+    <link rel="stylesheet" href="panel.css">
+  </head>
+  <body>
+    <main class="panel">
+      <header>
+        <h1 data-label="panelTitle">{{name}}</h1>
+        <p class="muted" data-label="panelDescription"></p>
+      </header>
+      <div class="actions">
+        <button type="button" data-button-id="refresh" data-label="refresh"></button>
+        <button type="button" data-button-id="add-item" data-label="addItem"></button>
+        <button type="button" class="danger" data-button-id="clear-items" data-label="clearItems"></button>
+      </div>
+      <p class="muted" id="empty-state" data-label="emptyState" hidden></p>
+      <table id="panel-table" hidden>
+        <thead>
+          <tr>
+            <th data-label="columnName"></th>
+            <th data-label="columnValue"></th>
+          </tr>
+        </thead>
+        <tbody></tbody>
+      </table>
+    </main>
+    <script src="panel.js"></script>
+  </body>
+</html>
+"#;
+
+const UI_PANEL_CSS: &str = r#"body {
+  margin: 0;
+  font-family: system-ui, sans-serif;
+  color: #1f2937;
+}
+
+.panel {
+  padding: 16px;
+}
+
+.muted {
+  color: #6b7280;
+}
+
+.actions {
+  display: flex;
+  gap: 8px;
+  margin: 12px 0;
+}
+
+button {
+  padding: 6px 12px;
+  border: 1px solid #d1d5db;
+  border-radius: 6px;
+  background: #fff;
+  cursor: pointer;
+}
+
+button.danger {
+  color: #b91c1c;
+}
+
+table {
+  width: 100%;
+  border-collapse: collapse;
+}
+
+th,
+td {
+  padding: 6px 8px;
+  border-bottom: 1px solid #e5e7eb;
+  text-align: left;
+}
+"#;
+
+const UI_PANEL_TS: &str = r#"// Iframe view of the panel, compiled to ../panel.js by `pnpm build`. Assets are
+// served through plugin_get_ui_asset and data comes from the `{{entry}}_view`
+// entry through the window.cognia bridge.
+
+interface PanelPayload {
+  state?: Record<string, unknown>;
+  rows?: string[][];
+  labels?: Record<string, string | undefined>;
+}
+
+interface PanelAction {
+  action?: string;
+  buttonId?: string;
+}
+
+interface CogniaBridge {
+  callTool(entry: string, input: string): Promise<unknown>;
+  log: { error(message: string): void };
+}
+
+interface Window {
+  cognia: CogniaBridge;
+}
+
+const VIEW_ENTRY = '{{entry}}_view';
+let panelState: Record<string, unknown> = {};
+
+function unwrapPayload(value: unknown): PanelPayload {
+  const raw =
+    value !== null && typeof value === 'object' && 'result' in value
+      ? (value as { result: unknown }).result
+      : value;
+  return (typeof raw === 'string' ? JSON.parse(raw) : raw) as PanelPayload;
+}
+
+function renderPanel(payload: PanelPayload): void {
+  const labels = payload.labels ?? {};
+  document.querySelectorAll<HTMLElement>('[data-label]').forEach((node) => {
+    const text = labels[node.dataset.label ?? ''];
+    if (text) node.textContent = text;
+  });
+
+  const rows = payload.rows ?? [];
+  const table = document.getElementById('panel-table') as HTMLTableElement;
+  table.tBodies[0].replaceChildren(
+    ...rows.map((row) => {
+      const tr = document.createElement('tr');
+      for (const cell of row) {
+        const td = document.createElement('td');
+        td.textContent = cell;
+        tr.appendChild(td);
+      }
+      return tr;
+    }),
+  );
+  table.hidden = rows.length === 0;
+  (document.getElementById('empty-state') as HTMLElement).hidden = rows.length !== 0;
+}
+
+function dispatch(action: PanelAction): Promise<void> {
+  const input = JSON.stringify({ action: 'initial_render', ...action, state: panelState });
+  return window.cognia
+    .callTool(VIEW_ENTRY, input)
+    .then((raw) => {
+      const payload = unwrapPayload(raw);
+      panelState = payload.state ?? {};
+      renderPanel(payload);
+    })
+    .catch((err: unknown) => {
+      window.cognia.log.error(String(err));
+    });
+}
+
+document.querySelectorAll<HTMLButtonElement>('[data-button-id]').forEach((button) => {
+  button.addEventListener('click', () => {
+    void dispatch({ action: 'button_click', buttonId: button.dataset.buttonId });
+  });
+});
+
+void dispatch({});
+"#;
+
 async fn generate_js_project(
     config: &ScaffoldConfig,
     plugin_dir: &Path,
@@ -2079,6 +3065,12 @@ async fn generate_js_project(
             serde_json::Value::String("node --test tests/contract-validation.test.js".to_string()),
         );
     }
+    if config.with_tests {
+        scripts.insert(
+            "test".to_string(),
+            serde_json::Value::String("node --test tests/plugin.test.js".to_string()),
+        );
+    }
 
     let package_json = serde_json::json!({
         "name": config.id,
@@ -2239,6 +3231,14 @@ async fn generate_ts_project(
             serde_json::Value::String("node --test tests/contract-validation.test.js".to_string()),
         );
     }
+    if config.with_tests {
+        scripts.insert(
+            "test".to_string(),
+            serde_json::Value::String(
+                "tsc -p tsconfig.test.json && tsx --test tests/plugin.test.ts".to_string(),
+            ),
+        );
+    }
     if config.template_options.include_ink_companion {
         scripts.insert(
             "authoring:ink".to_string(),
@@ -2256,6 +3256,20 @@ async fn generate_ts_project(
             serde_json::Value::String("^0.20.0".to_string()),
         ),
     ]);
+    if config.with_tests {
+        dev_dependencies.insert(
+            "@types/node".to_string(),
+            serde_json::Value::String("^20.0.0".to_string()),
+        );
+        dev_dependencies.insert(
+            "tsx".to_string(),
+            serde_json::Value::String("^4.21.0".to_string()),
+        );
+        dev_dependencies.insert(
+            "typescript".to_string(),
+            serde_json::Value::String("^5.4.0".to_string()),
+        );
+    }
     if config.template_options.include_ink_companion {
         dev_dependencies.insert(
             "ink".to_string(),
@@ -2354,6 +3368,9 @@ async fn generate_ts_project(
   interface user {{
     cognia_config_get(ptr: I64): I64;
     cognia_config_set(ptr: I64): I64;
+    cognia_config_get_typed(ptr: I64): I64;
+    cognia_config_set_typed(ptr: I64): I64;
+    cognia_config_all(ptr: I64): I64;
     cognia_env_list(ptr: I64): I64;
     cognia_provider_list(ptr: I64): I64;
     cognia_env_detect(ptr: I64): I64;
@@ -2369,12 +3386,18 @@ async fn generate_ts_project(
     cognia_pkg_check_updates(ptr: I64): I64;
     cognia_pkg_install(ptr: I64): I64;
     cognia_pkg_uninstall(ptr: I64): I64;
+    cognia_pkg_install_start(ptr: I64): I64;
+    cognia_pkg_operation_status(ptr: I64): I64;
     cognia_fs_read(ptr: I64): I64;
     cognia_fs_write(ptr: I64): I64;
     cognia_fs_list_dir(ptr: I64): I64;
     cognia_fs_exists(ptr: I64): I64;
     cognia_fs_delete(ptr: I64): I64;
     cognia_fs_mkdir(ptr: I64): I64;
+    cognia_storage_get(ptr: I64): I64;
+    cognia_storage_set(ptr: I64): I64;
+    cognia_storage_delete(ptr: I64): I64;
+    cognia_storage_list(ptr: I64): I64;
     cognia_http_get(ptr: I64): I64;
     cognia_http_post(ptr: I64): I64;
     cognia_http_request(ptr: I64): I64;
@@ -2387,6 +3410,7 @@ async fn generate_ts_project(
     cognia_get_locale(ptr: I64): I64;
     cognia_i18n_translate(ptr: I64): I64;
     cognia_i18n_get_all(ptr: I64): I64;
+    cognia_i18n_get_locales(ptr: I64): I64;
     cognia_platform_info(ptr: I64): I64;
     cognia_cache_info(ptr: I64): I64;
     cognia_log(ptr: I64): I64;
@@ -2867,6 +3891,41 @@ fn generate_readme(config: &ScaffoldConfig, handoff: &ScaffoldHandoff) -> String
         starter_test_command,
     );
 
+    let locale_files = scaffold_locales(config)
+        .iter()
+        .map(|locale| {
+            let language = match locale.as_str() {
+                "en" => " — English",
+                "zh" => " — Chinese",
+                _ => "",
+            };
+            format!("- `locales/{}.json`{}", locale, language)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let ui_panel_section = if matches!(config.template, ScaffoldTemplate::UiPanel) {
+        let tests_line = if config.with_tests {
+            "\nRun the panel unit tests on the host target (the default target is `wasm32-unknown-unknown`) and type-check the iframe script:\n\n```bash\ncargo test --target $(rustc -vV | sed -n 's/host: //p')\npnpm --dir ui typecheck\n```\n"
+        } else {
+            ""
+        };
+        format!(
+            r#"
+## UI Panel
+
+- `src/panel.rs` — panel state, button actions and declarative `ui` blocks
+- `src/lib.rs` — `{entry}` (declarative panel) and `{entry}_view` (iframe data source)
+- `ui/index.html`, `ui/panel.css` — iframe view served from the `[ui]` entry
+- `ui/src/panel.ts` — iframe script, compiled to `ui/panel.js` by `pnpm --dir ui build`
+- Panel strings are declared under `[locales.*]` in `plugin.toml` and mirrored in `locales/`
+{tests_line}"#,
+            entry = config.id.replace(['.', '-'], "_"),
+            tests_line = tests_line,
+        )
+    } else {
+        String::new()
+    };
+
     let lifecycle_title = match handoff.profile {
         ScaffoldLifecycleProfile::External => "## Lifecycle Handoff",
         ScaffoldLifecycleProfile::BuiltIn => "## Built-in Onboarding",
@@ -3011,9 +4070,8 @@ fn generate_readme(config: &ScaffoldConfig, handoff: &ScaffoldHandoff) -> String
 ## Locales
 
 Translation files are in the `locales/` directory:
-- `locales/en.json` — English
-- `locales/zh.json` — Chinese
-{contract_section}
+{locale_files}
+{ui_panel_section}{contract_section}
 {lifecycle_section}
 {project_links_section}
 {development_section}
@@ -3022,6 +4080,8 @@ fn generate_readme(config: &ScaffoldConfig, handoff: &ScaffoldHandoff) -> String
         name = config.name,
         desc = config.description,
         build = build_instructions,
+        locale_files = locale_files,
+        ui_panel_section = ui_panel_section,
         contract_section = contract_section,
         lifecycle_section = lifecycle_section,
         project_links_section = project_links_section,
@@ -3062,6 +4122,9 @@ fn base_scaffold_config(output_dir: &str) -> ScaffoldConfig {
             additional_keywords: Vec::new(),
             extension_points: Vec::new(),
             template_options: ScaffoldTemplateOptions::default(),
+            template: ScaffoldTemplate::Basic,
+            locales: default_locales(),
+            with_tests: false,
         }
     }
 
@@ -3088,6 +4151,9 @@ fn test_generate_manifest() {
             additional_keywords: vec!["utility".to_string(), "rust".to_string()],
             extension_points: Vec::new(),
             template_options: ScaffoldTemplateOptions::default(),
+            template: ScaffoldTemplate::Basic,
+            locales: default_locales(),
+            with_tests: false,
         };
         let manifest = generate_manifest(&config);
         assert!(manifest.contains("id = \"com.example.test\""));
@@ -3210,6 +4276,9 @@ fn test_generate_readme_rust() {
             additional_keywords: Vec::new(),
             extension_points: Vec::new(),
             template_options: ScaffoldTemplateOptions::default(),
+            template: ScaffoldTemplate::Basic,
+            locales: default_locales(),
+            with_tests: false,
         };
         let plugin_dir = PathBuf::from("/tmp/com.example.my");
         let handoff = build_scaffold_handoff(&config, &plugin_dir);
@@ -3244,6 +4313,9 @@ fn test_generate_readme_ts() {
             additional_keywords: Vec::new(),
             extension_points: Vec::new(),
             template_options: ScaffoldTemplateOptions::default(),
+            template: ScaffoldTemplate::Basic,
+            locales: default_locales(),
+            with_tests: false,
         };
         let plugin_dir = PathBuf::from("/tmp/com.example.ts");
         let handoff = build_scaffold_handoff(&config, &plugin_dir);
@@ -3271,6 +4343,9 @@ fn test_generate_readme_builtin_mentions_onboarding_flow() {
             additional_keywords: Vec::new(),
             extension_points: Vec::new(),
             template_options: ScaffoldTemplateOptions::default(),
+            template: ScaffoldTemplate::Basic,
+            locales: default_locales(),
+            with_tests: false,
         };
         let plugin_dir = PathBuf::from("/repo/plugins/typescript/sample");
         let handoff = build_scaffold_handoff(&config, &plugin_dir);
@@ -3323,6 +4398,9 @@ fn test_generate_readme_js() {
             additional_keywords: Vec::new(),
             extension_points: Vec::new(),
             template_options: ScaffoldTemplateOptions::default(),
+            template: ScaffoldTemplate::Basic,
+            locales: default_locales(),
+            with_tests: false,
         };
         let plugin_dir = PathBuf::from("/tmp/com.example.js");
         let handoff = build_scaffold_handoff(&config, &plugin_dir);
@@ -3439,6 +4517,9 @@ fn test_validate_scaffold_config_rejects_invalid_url() {
             additional_keywords: Vec::new(),
             extension_points: Vec::new(),
             template_options: ScaffoldTemplateOptions::default(),
+            template: ScaffoldTemplate::Basic,
+            locales: default_locales(),
+            with_tests: false,
         };
         let result = validate_scaffold_config(&config);
         assert!(result.is_err());
@@ -3463,6 +4544,9 @@ fn test_validate_scaffold_config_rejects_builtin_javascript_profile() {
             additional_keywords: Vec::new(),
             extension_points: Vec::new(),
             template_options: ScaffoldTemplateOptions::default(),
+            template: ScaffoldTemplate::Basic,
+            locales: default_locales(),
+            with_tests: false,
         };
         let result = validate_scaffold_config(&config);
         assert!(result.is_err());
@@ -3501,6 +4585,9 @@ fn test_validate_scaffold_config_rejects_builtin_framework_subdirectory_output()
             additional_keywords: Vec::new(),
             extension_points: Vec::new(),
             template_options: ScaffoldTemplateOptions::default(),
+            template: ScaffoldTemplate::Basic,
+            locales: default_locales(),
+            with_tests: false,
         };
         let result = validate_scaffold_config(&config);
         assert!(result.is_err());
@@ -3526,6 +4613,9 @@ fn test_validate_scaffold_config_rejects_invalid_id_characters() {
             additional_keywords: Vec::new(),
             extension_points: Vec::new(),
             template_options: ScaffoldTemplateOptions::default(),
+            template: ScaffoldTemplate::Basic,
+            locales: default_locales(),
+            with_tests: false,
         };
         let result = validate_scaffold_config(&config);
         assert!(result.is_err());
@@ -3580,6 +4670,9 @@ fn test_build_scaffold_handoff_external_includes_import_path() {
             additional_keywords: Vec::new(),
             extension_points: Vec::new(),
             template_options: ScaffoldTemplateOptions::default(),
+            template: ScaffoldTemplate::Basic,
+            locales: default_locales(),
+            with_tests: false,
         };
 
         let plugin_dir = PathBuf::from("/tmp/com.example.external");
@@ -3618,6 +4711,9 @@ fn test_build_scaffold_handoff_builtin_includes_onboarding_metadata() {
             additional_keywords: Vec::new(),
             extension_points: Vec::new(),
             template_options: ScaffoldTemplateOptions::default(),
+            template: ScaffoldTemplate::Basic,
+            locales: default_locales(),
+            with_tests: false,
         };
 
         let plugin_dir = PathBuf::from("/repo/plugins/rust/sample");
@@ -3698,6 +4794,9 @@ fn test_generate_readme_reuses_rust_handoff_build_commands() {
             additional_keywords: Vec::new(),
             extension_points: Vec::new(),
             template_options: ScaffoldTemplateOptions::default(),
+            template: ScaffoldTemplate::Basic,
+            locales: default_locales(),
+            with_tests: false,
         };
 
         let plugin_dir = PathBuf::from("/tmp/com.example.rust");
@@ -3894,4 +4993,196 @@ async fn test_scaffold_plugin_builtin_handoff_and_catalog_artifacts() {
 
         let _ = fs::remove_dir_all(&output_root);
     }
+
+    fn ui_panel_config(output_dir: &str) -> ScaffoldConfig {
+        let mut config = base_scaffold_config(output_dir);
+        config.id = "com.example.panel".to_string();
+        config.language = PluginLanguage::Rust;
+        config.template = ScaffoldTemplate::UiPanel;
+        config.locales = vec!["en".to_string(), "zh".to_string(), "ja".to_string()];
+        config
+    }
+
+    #[test]
+    fn test_generate_manifest_ui_panel_template_parses() {
+        let config = ui_panel_config("/tmp");
+        validate_scaffold_config(&config).expect("ui-panel config is valid");
+
+        let manifest =
+            crate::plugin::manifest::PluginManifest::from_str(&generate_manifest(&config))
+                .expect("ui-panel manifest parses");
+        assert_eq!(manifest.tools.len(), 2);
+        assert_eq!(
+            manifest.tools[0].ui_mode,
+            crate::plugin::manifest::UiMode::Declarative
+        );
+        assert_eq!(manifest.tools[1].entry, "com_example_panel_view");
+        assert_eq!(
+            manifest.tools[1].ui_mode,
+            crate::plugin::manifest::UiMode::Iframe
+        );
+        assert_eq!(
+            manifest.ui.as_ref().map(|ui| ui.entry.as_str()),
+            Some("ui/index.html")
+        );
+        assert_eq!(manifest.locales["zh"]["addItem"], "添加条目");
+        assert_eq!(manifest.locales["ja"]["addItem"], "Add item");
+    }
+
+    #[test]
+    fn test_validate_scaffold_config_ui_panel_constraints() {
+        let mut config = ui_panel_config("/tmp");
+        config.language = PluginLanguage::TypeScript;
+        let err = validate_scaffold_config(&config).unwrap_err().to_string();
+        assert!(err.contains("ui-panel template currently supports Rust only"));
+
+        let mut config = ui_panel_config("/tmp");
+        config.extension_points = vec!["tool-iframe-ui".to_string()];
+        let err = validate_scaffold_config(&config).unwrap_err().to_string();
+        assert!(err.contains("cannot use the 'tool-iframe-ui' extension point"));
+
+        let mut config = base_scaffold_config("/tmp");
+        config.with_tests = true;
+        assert!(validate_scaffold_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_validate_scaffold_config_rejects_empty_id_segments_and_bad_locales() {
+        let mut config = base_scaffold_config("/tmp");
+        config.id = "com..example".to_string();
+        let err = validate_scaffold_config(&config).unwrap_err().to_string();
+        assert!(err.contains("segments must not be empty"));
+
+        let mut config = base_scaffold_config("/tmp");
+        config.locales = vec!["en".to_string(), "../zh".to_string()];
+        let err = validate_scaffold_config(&config).unwrap_err().to_string();
+        assert!(err.contains("Invalid scaffold locale"));
+    }
+
+    #[tokio::test]
+    async fn test_scaffold_plugin_ui_panel_with_tests() {
+        let output_root = unique_temp_path("cognia_scaffold_ui_panel");
+        fs::create_dir_all(&output_root).expect("create output root");
+
+        let mut config = ui_panel_config(output_root.to_string_lossy().as_ref());
+        config.with_tests = true;
+
+        let result = scaffold_plugin(&config).await.expect("scaffold ui-panel");
+        let plugin_dir = PathBuf::from(&result.plugin_dir);
+
+        for relative in [
+            "src/lib.rs",
+            "src/panel.rs",
+            "ui/index.html",
+            "ui/panel.css",
+            "ui/src/panel.ts",
+            "ui/package.json",
+            "ui/tsconfig.json",
+            "tests/panel.rs",
+            "locales/en.json",
+            "locales/zh.json",
+            "locales/ja.json",
+        ] {
+            assert!(
+                result.files_created.iter().any(|file| file == relative),
+                "missing {}",
+                relative
+            );
+            assert!(plugin_dir.join(relative).exists(), "missing {}", relative);
+        }
+
+        let cargo_toml = fs::read_to_string(plugin_dir.join("Cargo.toml")).expect("Cargo.toml");
+        assert!(cargo_toml.contains("crate-type = [\"cdylib\", \"rlib\"]"));
+        let lib_rs = fs::read_to_string(plugin_dir.join("src/lib.rs")).expect("lib.rs");
+        assert!(lib_rs.contains("pub fn com_example_panel(input: String)"));
+        assert!(lib_rs.contains("pub fn com_example_panel_view(input: String)"));
+        let tests = fs::read_to_string(plugin_dir.join("tests/panel.rs")).expect("tests");
+        assert!(tests.contains("use com_example_panel::panel::"));
+        let panel_ts = fs::read_to_string(plugin_dir.join("ui/src/panel.ts")).expect("panel.ts");
+        assert!(panel_ts.contains("'com_example_panel_view'"));
+        assert!(result
+            .handoff
+            .build_commands
+            .iter()
+            .any(|command| command == "pnpm --dir ui build"));
+        typecheck_with_repo_tsc(&plugin_dir.join("ui"));
+        let ja: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(plugin_dir.join("locales/ja.json")).expect("ja.json"),
+        )
+        .expect("ja.json parses");
+        assert_eq!(ja["panelTitle"], "Sample Plugin");
+
+        let readme = fs::read_to_string(plugin_dir.join("README.md")).expect("README");
+        assert!(readme.contains("## UI Panel"));
+        assert!(readme.contains("`locales/ja.json`"));
+
+        let _ = fs::remove_dir_all(&output_root);
+    }
+
+    #[tokio::test]
+    async fn test_scaffold_plugin_basic_with_tests() {
+        let output_root = unique_temp_path("cognia_scaffold_basic_tests");
+        fs::create_dir_all(&output_root).expect("create output root");
+
+        let mut config = base_scaffold_config(output_root.to_string_lossy().as_ref());
+        config.language = PluginLanguage::Rust;
+        config.with_tests = true;
+        let result = scaffold_plugin(&config).await.expect("scaffold rust basic");
+        let plugin_dir = PathBuf::from(&result.plugin_dir);
+        assert!(result.files_created.iter().any(|f| f == "tests/plugin.rs"));
+        let tests = fs::read_to_string(plugin_dir.join("tests/plugin.rs")).expect("tests");
+        assert!(tests.contains("com_example_sample::tool_output("));
+        assert!(fs::read_to_string(plugin_dir.join("src/lib.rs"))
+            .expect("lib.rs")
+            .contains("pub fn tool_output(greeting: &str, os: &str, input: &str)"));
+
+        let mut config = base_scaffold_config(output_root.join("ts").to_string_lossy().as_ref());
+        config.with_tests = true;
+        let result = scaffold_plugin(&config).await.expect("scaffold ts basic");
+        let plugin_dir = PathBuf::from(&result.plugin_dir);
+        for relative in ["tests/plugin.test.ts", "tsconfig.test.json"] {
+            assert!(
+                result.files_created.iter().any(|f| f == relative),
+                "missing {}",
+                relative
+            );
+        }
+        let tests = fs::read_to_string(plugin_dir.join("tests/plugin.test.ts")).expect("tests");
+        assert!(tests.contains("plugin.com_example_sample()"));
+        assert!(tests.contains("require('../src/index')"));
+        let package: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(plugin_dir.join("package.json")).expect("package.json"),
+        )
+        .expect("package.json parses");
+        assert_eq!(
+            package["scripts"]["test"],
+            "tsc -p tsconfig.test.json && tsx --test tests/plugin.test.ts"
+        );
+        assert!(package["devDependencies"]["typescript"].is_string());
+
+        let _ = fs::remove_dir_all(&output_root);
+    }
+
+    /// Type-check a generated TypeScript project with the repository's own
+    /// compiler. Skipped when the frontend dependencies are not installed.
+    fn typecheck_with_repo_tsc(project: &Path) {
+        let tsc = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../node_modules/.bin")
+            .join(if cfg!(windows) { "tsc.cmd" } else { "tsc" });
+        if !tsc.exists() {
+            eprintln!("skipping type-check: {} not found", tsc.display());
+            return;
+        }
+        let output = std::process::Command::new(&tsc)
+            .arg("-p")
+            .arg(project)
+            .arg("--noEmit")
+            .output()
+            .expect("run tsc");
+        assert!(
+            output.status.success(),
+            "generated TypeScript failed to type-check:\n{}",
+            String::from_utf8_lossy(&output.stdout)
+        );
+    }
 }
@@ -0,0 +1,475 @@
+//! SQLite-backed key-value store for plugins.
+//!
+//! Every plugin gets a `.storage.db` database inside its data dir. Values live
+//! in one table keyed by the plugin's key, and triggers keep a running total of
+//! their sizes, so the quota check before each write reads a single row instead
+//! of summing the store.
+
+use chrono::Utc;
+use serde::Serialize;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, Connection, Row, SqliteConnection};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Database file (relative to the plugin data dir) holding the store.
+pub const STORAGE_DB_NAME: &str = ".storage.db";
+/// Default per-plugin quota in megabytes.
+pub const DEFAULT_STORAGE_QUOTA_MB: u64 = 10;
+/// Longest accepted key in bytes.
+pub const MAX_KEY_BYTES: usize = 1024;
+
+/// Characters of a value shown by the inspector.
+pub const INSPECT_PREVIEW_CHARS: usize = 200;
+
+const SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS entries (
+    key TEXT PRIMARY KEY,
+    value BLOB NOT NULL,
+    size INTEGER NOT NULL,
+    modified_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS usage (
+    id INTEGER PRIMARY KEY CHECK (id = 0),
+    bytes INTEGER NOT NULL,
+    keys INTEGER NOT NULL
+);
+INSERT OR IGNORE INTO usage (id, bytes, keys) VALUES (0, 0, 0);
+CREATE TRIGGER IF NOT EXISTS entries_insert AFTER INSERT ON entries BEGIN
+    UPDATE usage SET bytes = bytes + NEW.size, keys = keys + 1 WHERE id = 0;
+END;
+CREATE TRIGGER IF NOT EXISTS entries_update AFTER UPDATE ON entries BEGIN
+    UPDATE usage SET bytes = bytes - OLD.size + NEW.size WHERE id = 0;
+END;
+CREATE TRIGGER IF NOT EXISTS entries_delete AFTER DELETE ON entries BEGIN
+    UPDATE usage SET bytes = bytes - OLD.size, keys = keys - 1 WHERE id = 0;
+END;
+"#;
+
+#[derive(Debug, Error)]
+pub enum StorageError {
+    #[error("Invalid storage key: {0}")]
+    InvalidKey(String),
+    #[error(
+        "Storage quota exceeded: writing {required_bytes} bytes would exceed the {quota_bytes} byte quota ({usage_bytes} bytes used)"
+    )]
+    QuotaExceeded {
+        usage_bytes: u64,
+        quota_bytes: u64,
+        required_bytes: u64,
+    },
+    #[error("Storage database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("Storage I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// A stored key with its size, as reported to plugins and the inspector.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageEntry {
+    pub key: String,
+    pub size_bytes: u64,
+    pub modified_at: Option<String>,
+}
+
+/// Current usage of a plugin store against its quota.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageUsage {
+    pub usage_bytes: u64,
+    pub quota_bytes: u64,
+    pub key_count: usize,
+}
+
+/// A stored key as shown in the host-side inspector.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageInspectEntry {
+    #[serde(flatten)]
+    pub entry: StorageEntry,
+    /// Leading text of the value, or `None` when the value is not UTF-8.
+    pub preview: Option<String>,
+}
+
+/// Everything a plugin has stored, for the inspector.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginStorageInspection {
+    pub plugin_id: String,
+    pub usage: StorageUsage,
+    pub entries: Vec<StorageInspectEntry>,
+}
+
+/// Plugin data directory together with its storage usage.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginDataDirInfo {
+    pub path: String,
+    pub storage: StorageUsage,
+}
+
+/// Convert the configured quota in megabytes to bytes.
+pub fn quota_bytes_from_mb(quota_mb: u64) -> u64 {
+    quota_mb.saturating_mul(1024 * 1024)
+}
+
+fn validate_key(key: &str) -> Result<(), StorageError> {
+    if key.is_empty() {
+        return Err(StorageError::InvalidKey("key must not be empty".into()));
+    }
+    if key.len() > MAX_KEY_BYTES {
+        return Err(StorageError::InvalidKey(format!(
+            "key is {} bytes, maximum is {}",
+            key.len(),
+            MAX_KEY_BYTES
+        )));
+    }
+    Ok(())
+}
+
+/// Remove a plugin's store entirely. Missing stores are not an error.
+pub async fn remove_store(data_dir: &Path) -> Result<(), StorageError> {
+    let db = data_dir.join(STORAGE_DB_NAME);
+    let journal = data_dir.join(format!("{}-journal", STORAGE_DB_NAME));
+    for path in [db, journal] {
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(())
+}
+
+/// Handle to one plugin's store.
+#[derive(Debug, Clone)]
+pub struct PluginStore {
+    path: PathBuf,
+    quota_bytes: u64,
+}
+
+impl PluginStore {
+    pub fn new(data_dir: &Path, quota_bytes: u64) -> Self {
+        Self {
+            path: data_dir.join(STORAGE_DB_NAME),
+            quota_bytes,
+        }
+    }
+
+    /// Connect to the database, creating it when missing.
+    async fn open(&self) -> Result<SqliteConnection, StorageError> {
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let mut conn = SqliteConnectOptions::new()
+            .filename(&self.path)
+            .create_if_missing(true)
+            .connect()
+            .await?;
+        sqlx::raw_sql(SCHEMA).execute(&mut conn).await?;
+        Ok(conn)
+    }
+
+    /// Connect to the database if it exists, so reading or inspecting a
+    /// plugin that never stored anything does not create its store.
+    async fn open_existing(&self) -> Result<Option<SqliteConnection>, StorageError> {
+        if !tokio::fs::try_exists(&self.path).await? {
+            return Ok(None);
+        }
+        self.open().await.map(Some)
+    }
+
+    /// Read a value, returning `None` when the key is not stored.
+    pub async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        validate_key(key)?;
+        let Some(mut conn) = self.open_existing().await? else {
+            return Ok(None);
+        };
+        let value = sqlx::query_scalar("SELECT value FROM entries WHERE key = ?")
+            .bind(key)
+            .fetch_optional(&mut conn)
+            .await?;
+        conn.close().await?;
+        Ok(value)
+    }
+
+    /// Store a value, rejecting writes that would push usage over the quota.
+    pub async fn set(&self, key: &str, value: &[u8]) -> Result<(), StorageError> {
+        validate_key(key)?;
+        let mut conn = self.open().await?;
+
+        // Take the write lock up front so concurrent writers cannot both pass
+        // the quota check. Dropping the connection on error rolls back.
+        sqlx::query("BEGIN IMMEDIATE").execute(&mut conn).await?;
+        let usage_bytes = read_usage(&mut conn).await?.0;
+        let existing: i64 = sqlx::query_scalar("SELECT size FROM entries WHERE key = ?")
+            .bind(key)
+            .fetch_optional(&mut conn)
+            .await?
+            .unwrap_or(0);
+        let required_bytes = value.len() as u64;
+        if usage_bytes.saturating_sub(existing as u64) + required_bytes > self.quota_bytes {
+            sqlx::query("ROLLBACK").execute(&mut conn).await?;
+            conn.close().await?;
+            return Err(StorageError::QuotaExceeded {
+                usage_bytes,
+                quota_bytes: self.quota_bytes,
+                required_bytes,
+            });
+        }
+
+        sqlx::query(
+            "INSERT INTO entries (key, value, size, modified_at) VALUES (?, ?, ?, ?) \
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value, size = excluded.size, \
+             modified_at = excluded.modified_at",
+        )
+        .bind(key)
+        .bind(value)
+        .bind(required_bytes as i64)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&mut conn)
+        .await?;
+        sqlx::query("COMMIT").execute(&mut conn).await?;
+        conn.close().await?;
+        Ok(())
+    }
+
+    /// Delete a key. Returns whether it existed.
+    pub async fn delete(&self, key: &str) -> Result<bool, StorageError> {
+        validate_key(key)?;
+        let Some(mut conn) = self.open_existing().await? else {
+            return Ok(false);
+        };
+        let result = sqlx::query("DELETE FROM entries WHERE key = ?")
+            .bind(key)
+            .execute(&mut conn)
+            .await?;
+        conn.close().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// List stored keys starting with `prefix`, sorted by key.
+    pub async fn list(&self, prefix: &str) -> Result<Vec<StorageEntry>, StorageError> {
+        let Some(mut conn) = self.open_existing().await? else {
+            return Ok(Vec::new());
+        };
+        let rows = sqlx::query(
+            "SELECT key, size, modified_at FROM entries \
+             WHERE substr(key, 1, length(?1)) = ?1 ORDER BY key",
+        )
+        .bind(prefix)
+        .fetch_all(&mut conn)
+        .await?;
+        conn.close().await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| StorageEntry {
+                key: row.get("key"),
+                size_bytes: row.get::<i64, _>("size") as u64,
+                modified_at: row.get("modified_at"),
+            })
+            .collect())
+    }
+
+    /// Current usage against the quota.
+    pub async fn usage(&self) -> Result<StorageUsage, StorageError> {
+        let (usage_bytes, key_count) = match self.open_existing().await? {
+            Some(mut conn) => {
+                let usage = read_usage(&mut conn).await?;
+                conn.close().await?;
+                usage
+            }
+            None => (0, 0),
+        };
+        Ok(StorageUsage {
+            usage_bytes,
+            quota_bytes: self.quota_bytes,
+            key_count,
+        })
+    }
+
+    /// List every key with a short text preview of its value.
+    pub async fn inspect(&self) -> Result<Vec<StorageInspectEntry>, StorageError> {
+        let mut inspected = Vec::new();
+        for entry in self.list("").await? {
+            let preview = self
+                .get(&entry.key)
+                .await?
+                .and_then(|bytes| String::from_utf8(bytes).ok())
+                .map(|text| text.chars().take(INSPECT_PREVIEW_CHARS).collect());
+            inspected.push(StorageInspectEntry { entry, preview });
+        }
+        Ok(inspected)
+    }
+
+    /// Delete the given keys, or every key when `keys` is `None`.
+    /// Returns how many keys were removed.
+    pub async fn clear(&self, keys: Option<&[String]>) -> Result<usize, StorageError> {
+        let Some(keys) = keys else {
+            let Some(mut conn) = self.open_existing().await? else {
+                return Ok(0);
+            };
+            let result = sqlx::query("DELETE FROM entries")
+                .execute(&mut conn)
+                .await?;
+            conn.close().await?;
+            return Ok(result.rows_affected() as usize);
+        };
+
+        let mut removed = 0;
+        for key in keys {
+            if self.delete(key).await? {
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+/// Stored bytes and key count, as maintained by the triggers.
+async fn read_usage(conn: &mut SqliteConnection) -> Result<(u64, usize), StorageError> {
+    let row = sqlx::query("SELECT bytes, keys FROM usage WHERE id = 0")
+        .fetch_one(conn)
+        .await?;
+    Ok((
+        row.get::<i64, _>("bytes").max(0) as u64,
+        row.get::<i64, _>("keys").max(0) as usize,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_set_get_delete_roundtrip() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = PluginStore::new(tmp.path(), 1024);
+
+        assert_eq!(store.get("missing").await.unwrap(), None);
+        store.set("a/b", &[0, 1, 2, 255]).await.unwrap();
+        assert_eq!(store.get("a/b").await.unwrap(), Some(vec![0, 1, 2, 255]));
+
+        assert!(store.delete("a/b").await.unwrap());
+        assert!(!store.delete("a/b").await.unwrap());
+        assert_eq!(store.get("a/b").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_list_filters_by_prefix_and_sorts() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = PluginStore::new(tmp.path(), 1024);
+        store.set("cache:b", b"22").await.unwrap();
+        store.set("cache:a", b"1").await.unwrap();
+        store.set("state", b"333").await.unwrap();
+
+        let keys: Vec<String> = store
+            .list("cache:")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.key)
+            .collect();
+        assert_eq!(keys, vec!["cache:a", "cache:b"]);
+
+        let usage = store.usage().await.unwrap();
+        assert_eq!(usage.usage_bytes, 6);
+        assert_eq!(usage.key_count, 3);
+    }
+
+    #[tokio::test]
+    async fn test_quota_enforced_at_write_time() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = PluginStore::new(tmp.path(), 10);
+        store.set("a", &[0; 6]).await.unwrap();
+
+        match store.set("b", &[0; 5]).await {
+            Err(StorageError::QuotaExceeded {
+                usage_bytes,
+                quota_bytes,
+                required_bytes,
+            }) => {
+                assert_eq!(usage_bytes, 6);
+                assert_eq!(quota_bytes, 10);
+                assert_eq!(required_bytes, 5);
+            }
+            other => panic!("expected quota error, got {:?}", other),
+        }
+
+        // Overwriting an existing key only counts the size difference.
+        store.set("a", &[0; 10]).await.unwrap();
+        assert_eq!(store.usage().await.unwrap().usage_bytes, 10);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_keys_rejected() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = PluginStore::new(tmp.path(), 1024);
+        assert!(matches!(
+            store.set("", b"x").await,
+            Err(StorageError::InvalidKey(_))
+        ));
+        let long = "k".repeat(MAX_KEY_BYTES + 1);
+        assert!(matches!(
+            store.get(&long).await,
+            Err(StorageError::InvalidKey(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reads_do_not_create_store() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = PluginStore::new(tmp.path(), 1024);
+        assert_eq!(store.get("a").await.unwrap(), None);
+        assert!(store.list("").await.unwrap().is_empty());
+        assert_eq!(store.usage().await.unwrap().key_count, 0);
+        assert!(!tmp.path().join(STORAGE_DB_NAME).exists());
+    }
+
+    #[tokio::test]
+    async fn test_list_prefix_is_literal() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = PluginStore::new(tmp.path(), 1024);
+        store.set("a%b", b"1").await.unwrap();
+        store.set("axb", b"1").await.unwrap();
+        store.set("A%B", b"1").await.unwrap();
+
+        let keys: Vec<String> = store
+            .list("a%")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.key)
+            .collect();
+        assert_eq!(keys, vec!["a%b"]);
+    }
+
+    #[tokio::test]
+    async fn test_clear_and_remove_store() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = PluginStore::new(tmp.path(), 1024);
+        store.set("a", b"1").await.unwrap();
+        store.set("b", b"2").await.unwrap();
+        store.set("c", b"3").await.unwrap();
+
+        let removed = store.clear(Some(&["a".to_string()])).await.unwrap();
+        assert_eq!(removed, 1);
+        assert_eq!(store.usage().await.unwrap().key_count, 2);
+
+        assert_eq!(store.clear(None).await.unwrap(), 2);
+        assert_eq!(store.usage().await.unwrap().key_count, 0);
+
+        store.set("text", b"hello").await.unwrap();
+        store.set("blob", &[0xff, 0xfe]).await.unwrap();
+        let inspected = store.inspect().await.unwrap();
+        assert_eq!(inspected[0].entry.key, "blob");
+        assert_eq!(inspected[0].preview, None);
+        assert_eq!(inspected[1].preview.as_deref(), Some("hello"));
+
+        remove_store(tmp.path()).await.unwrap();
+        assert!(!tmp.path().join(STORAGE_DB_NAME).exists());
+        remove_store(tmp.path()).await.unwrap();
+    }
+}
@@ -7,6 +7,7 @@
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
@@ -24,16 +25,24 @@
 pub struct AdoptiumProvider {
     jdks_dir: Option<PathBuf>,
     client: Client,
+    /// Download cache that keeps install archives for installed versions
+    cache_dir: Option<PathBuf>,
 }
 
 impl AdoptiumProvider {
     pub fn new() -> Self {
         Self {
             jdks_dir: Self::detect_jdks_dir(),
-            client: crate::platform::proxy::get_shared_client(),
+            client: crate::platform::proxy::get_client_for_provider("adoptium"),
+            cache_dir: None,
         }
     }
 
+    pub fn with_cache_dir(mut self, cache_dir: Option<PathBuf>) -> Self {
+        self.cache_dir = cache_dir;
+        self
+    }
+
     fn detect_jdks_dir() -> Option<PathBuf> {
         crate::platform::fs::get_cognia_dir().map(|d| d.join("jdks"))
     }
@@ -448,6 +457,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::List,
             Capability::VersionSwitch,
             Capability::MultiVersion,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
 
@@ -601,27 +612,58 @@ async fn install(&self, req: InstallRequest) -> CogniaResult<InstallReceipt> {
             });
         }
 
-        // Download the archive
+        // The download cache is keyed by SHA256, so only checksummed packages use it
         let download_url = &asset.binary.package.link;
-        let response = self
-            .client
-            .get(download_url)
-            .timeout(Duration::from_secs(600))
-            .send()
-            .await
-            .map_err(|e| CogniaError::Network(e.to_string()))?;
+        let checksum = asset
+            .binary
+            .package
+            .checksum
+            .as_deref()
+            .map(str::to_ascii_lowercase)
+            .filter(|c| c.len() == 64 && c.bytes().all(|b| b.is_ascii_hexdigit()));
+        let cache = self.cache_dir.as_deref().zip(checksum.as_deref());
+        let cached = match cache {
+            Some((cache_dir, checksum)) => {
+                crate::cache::download::cached_env_artifact(cache_dir, checksum).await
+            }
+            None => None,
+        };
 
-        if !response.status().is_success() {
-            return Err(CogniaError::Network(format!(
-                "Download failed: {}",
-                response.status()
-            )));
-        }
+        // Download the archive unless an earlier install cached it
+        let bytes = if let Some(cached) = cached {
+            tokio::fs::read(&cached).await?
+        } else {
+            let response = self
+                .client
+                .get(download_url)
+                .timeout(Duration::from_secs(600))
+                .send()
+                .await
+                .map_err(|e| CogniaError::Network(e.to_string()))?;
 
-        let bytes = response
-            .bytes()
-            .await
-            .map_err(|e| CogniaError::Network(e.to_string()))?;
+            if !response.status().is_success() {
+                return Err(CogniaError::Network(format!(
+                    "Download failed: {}",
+                    response.status()
+                )));
+            }
+
+            response
+                .bytes()
+                .await
+                .map_err(|e| CogniaError::Network(e.to_string()))?
+                .to_vec()
+        };
+
+        if let Some(expected) = checksum.as_deref() {
+            let actual = hex::encode(Sha256::digest(&bytes));
+            if actual != expected {
+                return Err(CogniaError::Provider(format!(
+                    "Checksum mismatch for JDK {}: expected {}, got {}",
+                    actual_version, expected, actual
+                )));
+            }
+        }
 
         // Ensure versions directory exists
         tokio::fs::create_dir_all(&versions_dir)
@@ -653,10 +695,19 @@ async fn install(&self, req: InstallRequest) -> CogniaResult<InstallReceipt> {
         tokio::fs::write(&archive_path, &bytes)
             .await
             .map_err(|e| CogniaError::Io(std::io::Error::other(e.to_string())))?;
+        if let Some((cache_dir, checksum)) = cache {
+            crate::cache::download::store_env_artifact(
+                cache_dir,
+                &archive_path,
+                checksum,
+                download_url,
+            )
+            .await;
+        }
 
         // Extract using shared infrastructure
         let extract_result =
-            crate::core::installer::extract_archive(&archive_path, &temp_dir).await;
+            crate::core::archive::extract_archive(&archive_path, &temp_dir).await;
         let _ = tokio::fs::remove_file(&archive_path).await;
         extract_result.map_err(|e| CogniaError::Provider(format!("Extraction failed: {}", e)))?;
 
@@ -674,6 +725,16 @@ async fn install(&self, req: InstallRequest) -> CogniaResult<InstallReceipt> {
 
         let _ = tokio::fs::remove_dir_all(&temp_dir).await;
 
+        if let Some((cache_dir, checksum)) = cache {
+            crate::cache::download::reference_env_artifact(
+                cache_dir,
+                checksum,
+                "java",
+                &actual_version,
+            )
+            .await;
+        }
+
         Ok(InstallReceipt {
             name: "jdk".to_string(),
             version: actual_version,
@@ -721,6 +782,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                 install_path: path,
                 installed_at: String::new(),
                 is_global: true,
+                kind: None,
             })
             .collect();
 
@@ -1,4 +1,5 @@
 use crate::error::{CogniaError, CogniaResult};
+use crate::platform::http_replay::send;
 use reqwest::Client;
 use serde::Deserialize;
 use std::sync::RwLock;
@@ -86,6 +87,12 @@ fn get_client(&self) -> Client {
             .unwrap_or_else(|e| e.into_inner().clone())
     }
 
+    /// Client for a registry, honouring its per-provider proxy override
+    fn client_for(&self, provider: &str) -> Client {
+        crate::platform::proxy::provider_override_client(provider)
+            .unwrap_or_else(|| self.get_client())
+    }
+
     /// Update the configuration at runtime
     pub fn update_config(&self, config: ApiClientConfig) {
         if let Ok(mut guard) = self.config.write() {
@@ -115,7 +122,7 @@ pub fn set_crates_registry(&self, url: &str) {
     }
 
     /// Get the current PyPI base URL
-    fn get_pypi_url(&self) -> String {
+    pub(crate) fn get_pypi_url(&self) -> String {
         self.config
             .read()
             .map(|c| c.pypi_base_url.clone())
@@ -123,7 +130,7 @@ fn get_pypi_url(&self) -> String {
     }
 
     /// Get the current npm registry URL
-    fn get_npm_registry(&self) -> String {
+    pub(crate) fn get_npm_registry(&self) -> String {
         self.config
             .read()
             .map(|c| c.npm_registry_url.clone())
@@ -131,7 +138,7 @@ fn get_npm_registry(&self) -> String {
     }
 
     /// Get the current crates.io registry URL
-    fn get_crates_registry(&self) -> String {
+    pub(crate) fn get_crates_registry(&self) -> String {
         self.config
             .read()
             .map(|c| c.crates_registry_url.clone())
@@ -144,7 +151,7 @@ pub async fn search_pypi(&self, query: &str, limit: usize) -> CogniaResult<Vec<P
 
         // 1. Try exact match first (fast, returns full metadata)
         let url = format!("{}/pypi/{}/json", base_url, query);
-        let exact_match = match self.get_client().get(&url).send().await {
+        let exact_match = match send(self.client_for("pypi").get(&url)).await {
             Ok(response) if response.status().is_success() => {
                 let data: PyPIResponse = response.json().await.map_err(|e| {
                     CogniaError::Provider(format!("Failed to parse PyPI response: {}", e))
@@ -171,7 +178,7 @@ pub async fn search_pypi(&self, query: &str, limit: usize) -> CogniaResult<Vec<P
         // Only do fuzzy search if we need more results
         if results.len() < limit {
             let search_url = format!("{}/search/?q={}", base_url, query);
-            if let Ok(response) = self.get_client().get(&search_url).send().await {
+            if let Ok(response) = send(self.client_for("pypi").get(&search_url)).await {
                 if response.status().is_success() {
                     if let Ok(html) = response.text().await {
                         let fuzzy = Self::parse_pypi_search_html(&html, limit);
@@ -260,10 +267,7 @@ pub async fn get_pypi_package(&self, name: &str) -> CogniaResult<PyPIPackage> {
         let base_url = self.get_pypi_url();
         let url = format!("{}/pypi/{}/json", base_url, name);
 
-        let response = self
-            .get_client()
-            .get(&url)
-            .send()
+        let response = send(self.client_for("pypi").get(&url))
             .await
             .map_err(|e| CogniaError::Provider(format!("PyPI API request failed: {}", e)))?;
 
@@ -297,7 +301,7 @@ pub async fn search_npm(&self, query: &str, limit: usize) -> CogniaResult<Vec<Np
             limit
         );
 
-        let response = self.get_client().get(&url).send().await.map_err(|e| {
+        let response = send(self.client_for("npm").get(&url)).await.map_err(|e| {
             CogniaError::Provider(format!("npm registry API request failed: {}", e))
         })?;
 
@@ -336,7 +340,7 @@ pub async fn get_npm_package(&self, name: &str) -> CogniaResult<NpmPackageInfo>
         let registry_url = self.get_npm_registry();
         let url = format!("{}/{}", registry_url, urlencoding::encode(name));
 
-        let response = self.get_client().get(&url).send().await.map_err(|e| {
+        let response = send(self.client_for("npm").get(&url)).await.map_err(|e| {
             CogniaError::Provider(format!("npm registry API request failed: {}", e))
         })?;
 
@@ -376,16 +380,12 @@ pub async fn search_crates(
             limit
         );
 
-        let response = self
-            .get_client()
-            .get(&url)
-            .header(
-                "User-Agent",
-                "CogniaLauncher/0.1.0 (https://github.com/AstroAir/CogniaLauncher)",
-            )
-            .send()
-            .await
-            .map_err(|e| CogniaError::Provider(format!("crates.io API request failed: {}", e)))?;
+        let response = send(self.client_for("crates").get(&url).header(
+            "User-Agent",
+            "CogniaLauncher/0.1.0 (https://github.com/AstroAir/CogniaLauncher)",
+        ))
+        .await
+        .map_err(|e| CogniaError::Provider(format!("crates.io API request failed: {}", e)))?;
 
         if !response.status().is_success() {
             return Err(CogniaError::Provider(format!(
@@ -415,16 +415,12 @@ pub async fn search_crates(
     /// Perform a raw GET request and return the response body as a string
     /// Useful for APIs that don't fit the standard package registry pattern
     pub async fn raw_get(&self, url: &str) -> CogniaResult<String> {
-        let response = self
-            .get_client()
-            .get(url)
-            .header(
-                "User-Agent",
-                "CogniaLauncher/0.1.0 (https://github.com/AstroAir/CogniaLauncher)",
-            )
-            .send()
-            .await
-            .map_err(|e| CogniaError::Provider(format!("HTTP request failed: {}", e)))?;
+        let response = send(self.get_client().get(url).header(
+            "User-Agent",
+            "CogniaLauncher/0.1.0 (https://github.com/AstroAir/CogniaLauncher)",
+        ))
+        .await
+        .map_err(|e| CogniaError::Provider(format!("HTTP request failed: {}", e)))?;
 
         if !response.status().is_success() {
             return Err(CogniaError::Provider(format!(
@@ -449,16 +445,12 @@ pub async fn get_crate(&self, name: &str) -> CogniaResult<CrateInfo> {
             urlencoding::encode(name)
         );
 
-        let response = self
-            .get_client()
-            .get(&url)
-            .header(
-                "User-Agent",
-                "CogniaLauncher/0.1.0 (https://github.com/AstroAir/CogniaLauncher)",
-            )
-            .send()
-            .await
-            .map_err(|e| CogniaError::Provider(format!("crates.io API request failed: {}", e)))?;
+        let response = send(self.client_for("crates").get(&url).header(
+            "User-Agent",
+            "CogniaLauncher/0.1.0 (https://github.com/AstroAir/CogniaLauncher)",
+        ))
+        .await
+        .map_err(|e| CogniaError::Provider(format!("crates.io API request failed: {}", e)))?;
 
         if !response.status().is_success() {
             return Err(CogniaError::PackageNotFound(name.to_string()));
@@ -98,6 +98,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::Update,
             Capability::Upgrade,
             Capability::UpdateIndex,
+            Capability::Info,
+            Capability::RequiresElevation,
         ])
     }
 
@@ -288,6 +290,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                     install_path: PathBuf::from("/usr"),
                     installed_at: String::new(),
                     is_global: true,
+                    kind: None,
                 })
             })
             .collect())
@@ -496,7 +499,7 @@ fn test_capabilities() {
         assert!(caps.contains(&Capability::Update));
         assert!(caps.contains(&Capability::Upgrade));
         assert!(caps.contains(&Capability::UpdateIndex));
-        assert_eq!(caps.len(), 7);
+        assert_eq!(caps.len(), 9);
     }
 
     #[test]
@@ -608,6 +611,7 @@ fn test_parse_apk_list_installed_output() {
                     install_path: PathBuf::from("/usr"),
                     installed_at: String::new(),
                     is_global: true,
+                    kind: None,
                 })
             })
             .collect();
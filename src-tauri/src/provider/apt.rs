@@ -83,6 +83,9 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::Update,
             Capability::Upgrade,
             Capability::UpdateIndex,
+            Capability::Info,
+            Capability::Versions,
+            Capability::RequiresElevation,
         ])
     }
     fn supported_platforms(&self) -> Vec<Platform> {
@@ -271,6 +274,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                         install_path: PathBuf::from("/usr"),
                         installed_at: String::new(),
                         is_global: true,
+                        kind: None,
                     })
                 } else {
                     None
@@ -440,7 +444,9 @@ fn test_capabilities() {
         assert!(caps.contains(&Capability::Update));
         assert!(caps.contains(&Capability::Upgrade));
         assert!(caps.contains(&Capability::UpdateIndex));
-        assert_eq!(caps.len(), 7);
+        assert!(caps.contains(&Capability::Versions));
+        assert!(caps.contains(&Capability::RequiresElevation));
+        assert_eq!(caps.len(), 10);
     }
 
     #[test]
@@ -520,6 +526,7 @@ fn test_parse_dpkg_list_output() {
                         install_path: PathBuf::from("/usr"),
                         installed_at: String::new(),
                         is_global: true,
+                        kind: None,
                     })
                 } else {
                     None
@@ -94,6 +94,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::List,
             Capability::VersionSwitch,
             Capability::MultiVersion,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
     fn supported_platforms(&self) -> Vec<Platform> {
@@ -270,6 +272,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                         install_path: asdf_dir.join("installs").join(plugin).join(version),
                         installed_at: String::new(),
                         is_global: true,
+                        kind: None,
                     });
                 }
             }
@@ -129,6 +129,7 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::Update,
             Capability::Upgrade,
             Capability::UpdateIndex,
+            Capability::Info,
         ])
     }
     fn supported_platforms(&self) -> Vec<Platform> {
@@ -311,6 +312,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                         install_path: brew_prefix.join("Cellar").join(&name),
                         installed_at: String::new(),
                         is_global: true,
+                        kind: Some("formula".into()),
                     });
                 }
             }
@@ -334,6 +336,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                         install_path: brew_prefix.join("Caskroom").join(&name),
                         installed_at: String::new(),
                         is_global: true,
+                        kind: Some("cask".into()),
                     });
                 }
             }
@@ -515,7 +518,7 @@ fn test_capabilities() {
         assert!(caps.contains(&Capability::Update));
         assert!(caps.contains(&Capability::Upgrade));
         assert!(caps.contains(&Capability::UpdateIndex));
-        assert_eq!(caps.len(), 7);
+        assert_eq!(caps.len(), 8);
     }
 
     #[test]
@@ -595,6 +598,7 @@ fn test_parse_list_versions_output() {
                         install_path: brew_prefix.join("Cellar").join(&name),
                         installed_at: String::new(),
                         is_global: true,
+                        kind: None,
                     })
                 } else {
                     None
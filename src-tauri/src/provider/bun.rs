@@ -164,6 +164,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::List,
             Capability::Update,
             Capability::Upgrade,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
 
@@ -260,7 +262,7 @@ async fn get_dependencies(&self, name: &str, version: &str) -> CogniaResult<Vec<
             .unwrap_or(super::api::DEFAULT_NPM_REGISTRY);
         let url = format!("{}/{}", registry_url, pkg);
 
-        let client = crate::platform::proxy::get_shared_client();
+        let client = crate::platform::proxy::get_client_for_provider("bun");
 
         if let Ok(resp) = client
             .get(&url)
@@ -389,6 +391,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                 install_path,
                 installed_at: String::new(),
                 is_global: filter.global_only,
+                kind: None,
             });
         }
 
@@ -23,7 +23,7 @@ pub struct BundlerProvider {
 impl BundlerProvider {
     pub fn new() -> Self {
         Self {
-            client: crate::platform::proxy::get_shared_client(),
+            client: crate::platform::proxy::get_client_for_provider("bundler"),
         }
     }
 
@@ -189,6 +189,7 @@ fn parse_bundle_list_output(output: &str) -> Vec<InstalledPackage> {
                         install_path,
                         installed_at: String::new(),
                         is_global: false,
+                        kind: None,
                     });
                 }
             }
@@ -311,6 +312,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::List,
             Capability::Update,
             Capability::LockVersion,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
 
@@ -378,7 +381,7 @@ async fn get_versions(&self, name: &str) -> CogniaResult<Vec<VersionInfo>> {
     async fn get_dependencies(&self, name: &str, _version: &str) -> CogniaResult<Vec<Dependency>> {
         // Use rubygems.org API to get gem dependencies
         let url = format!("https://rubygems.org/api/v1/gems/{}.json", name);
-        let client = crate::platform::proxy::get_shared_client();
+        let client = crate::platform::proxy::get_client_for_provider("bundler");
 
         if let Ok(resp) = client
             .get(&url)
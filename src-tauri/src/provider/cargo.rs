@@ -8,7 +8,7 @@
 use crate::resolver::{Dependency, VersionConstraint};
 use async_trait::async_trait;
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 /// Cargo - Rust Package Manager
@@ -97,21 +97,24 @@ pub fn get_registry_url(&self) -> Option<&String> {
     async fn get_installed_crate_version(&self, name: &str) -> CogniaResult<String> {
         let out = self.run_cargo(&["install", "--list"]).await?;
 
-        for line in out.lines() {
-            if !line.starts_with(' ') && !line.is_empty() {
-                // Format: "crate_name v0.1.0:"
-                let parts: Vec<&str> = line.trim_end_matches(':').split_whitespace().collect();
-                if parts.len() >= 2 && parts[0] == name {
-                    return Ok(parts[1].trim_start_matches('v').to_string());
-                }
+        parse_installed_crate_version(&out, name).ok_or_else(|| {
+            CogniaError::Provider(format!("Crate {} not found in installed list", name))
+        })
+    }
+}
+
+/// Find `name` in `cargo install --list` output
+fn parse_installed_crate_version(out: &str, name: &str) -> Option<String> {
+    for line in out.lines() {
+        if !line.starts_with(' ') && !line.is_empty() {
+            // Format: "crate_name v0.1.0:"
+            let parts: Vec<&str> = line.trim_end_matches(':').split_whitespace().collect();
+            if parts.len() >= 2 && parts[0] == name {
+                return Some(parts[1].trim_start_matches('v').to_string());
             }
         }
-
-        Err(CogniaError::Provider(format!(
-            "Crate {} not found in installed list",
-            name
-        )))
     }
+    None
 }
 
 impl Default for CargoProvider {
@@ -136,6 +139,9 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::List,
             Capability::Update,
             Capability::Upgrade,
+            Capability::Info,
+            Capability::Versions,
+            Capability::SupportsPrefix,
         ])
     }
     fn supported_platforms(&self) -> Vec<Platform> {
@@ -367,6 +373,38 @@ async fn get_installed_version(&self, name: &str) -> CogniaResult<Option<String>
         }
     }
 
+    async fn verify_installed(
+        &self,
+        name: &str,
+        expected_version: &str,
+    ) -> CogniaResult<PackageVerification> {
+        let out = self.run_cargo(&["install", "--list"]).await?;
+        let Some((installed, binaries)) = parse_installed_binaries(&out, name) else {
+            return Ok(PackageVerification::failed(
+                "installed_version",
+                format!("{} is no longer reported as installed", name),
+            ));
+        };
+        if !versions_match(&installed, expected_version) {
+            return Ok(PackageVerification::failed(
+                "installed_version",
+                format!(
+                    "Expected {} {} but {} is installed",
+                    name, expected_version, installed
+                ),
+            ));
+        }
+
+        let Some(bin_dir) = Self::get_cargo_home().map(|home| home.join("bin")) else {
+            return Ok(PackageVerification::inconclusive(
+                "binary",
+                "Could not determine CARGO_HOME",
+            ));
+        };
+        let paths: Vec<PathBuf> = binaries.iter().map(|bin| bin_dir.join(bin)).collect();
+        Ok(verify_binaries_run(&paths).await)
+    }
+
     async fn get_dependencies(&self, name: &str, version: &str) -> CogniaResult<Vec<Dependency>> {
         // Use crates.io API to get dependencies
         let api = get_api_client();
@@ -383,7 +421,7 @@ async fn get_dependencies(&self, name: &str, version: &str) -> CogniaResult<Vec<
                 name, target_version
             );
 
-            let client = crate::platform::proxy::get_shared_client();
+            let client = crate::platform::proxy::get_client_for_provider("cargo");
 
             if let Ok(resp) = client
                 .get(&url)
@@ -454,6 +492,43 @@ async fn install(&self, req: InstallRequest) -> CogniaResult<InstallReceipt> {
         })
     }
 
+    /// `cargo install --root` puts the binaries in `<prefix>/bin` and keeps
+    /// its own install tracking inside the prefix
+    async fn install_to_prefix(
+        &self,
+        req: InstallRequest,
+        prefix: &Path,
+    ) -> CogniaResult<InstallReceipt> {
+        let root = prefix.to_string_lossy().to_string();
+        let mut args = vec!["install", "--root", &root, &req.name];
+        let ver;
+        if let Some(v) = &req.version {
+            ver = format!("--version={}", v);
+            args.push(&ver);
+        }
+        if req.force {
+            args.push("--force");
+        }
+
+        self.run_cargo(&args).await?;
+
+        let actual_version = self
+            .run_cargo(&["install", "--list", "--root", &root])
+            .await
+            .ok()
+            .and_then(|out| parse_installed_crate_version(&out, &req.name))
+            .unwrap_or_else(|| req.version.clone().unwrap_or_else(|| "unknown".into()));
+
+        Ok(InstallReceipt {
+            name: req.name,
+            version: actual_version,
+            provider: self.id().into(),
+            install_path: prefix.join("bin"),
+            files: vec![],
+            installed_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
     async fn uninstall(&self, req: UninstallRequest) -> CogniaResult<()> {
         self.run_cargo(&["uninstall", &req.name]).await?;
         Ok(())
@@ -486,6 +561,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                                 install_path: cargo_bin.clone(),
                                 installed_at: String::new(),
                                 is_global: true,
+                                kind: None,
                             });
                         }
                     }
@@ -515,6 +591,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                         install_path: cargo_bin,
                         installed_at: String::new(),
                         is_global: true,
+                        kind: None,
                     });
                 }
             }
@@ -643,6 +720,29 @@ pub(crate) fn parse_installed_list_output(output: &str) -> Vec<(String, String)>
     packages
 }
 
+/// Find a crate's version and binaries in `cargo install --list` output
+pub(crate) fn parse_installed_binaries(output: &str, name: &str) -> Option<(String, Vec<String>)> {
+    let mut lines = output.lines();
+    while let Some(line) = lines.next() {
+        if line.starts_with(' ') || line.is_empty() {
+            continue;
+        }
+        let parts: Vec<&str> = line.trim_end_matches(':').split_whitespace().collect();
+        if parts.len() < 2 || parts[0] != name {
+            continue;
+        }
+
+        let version = parts[1].trim_start_matches('v').to_string();
+        let binaries = lines
+            .take_while(|l| l.starts_with(' '))
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty())
+            .collect();
+        return Some((version, binaries));
+    }
+    None
+}
+
 /// Check if a crate name matches exactly in `cargo install --list` output
 pub(crate) fn is_crate_in_installed_output(output: &str, name: &str) -> bool {
     output.lines().any(|l| {
@@ -678,6 +778,16 @@ fn test_cargo_provider_with_env() {
         );
     }
 
+    #[test]
+    fn test_parse_installed_crate_version() {
+        let out = "bat v0.24.0:\n    bat\nripgrep v14.1.0:\n    rg\n";
+        assert_eq!(
+            parse_installed_crate_version(out, "ripgrep"),
+            Some("14.1.0".to_string())
+        );
+        assert_eq!(parse_installed_crate_version(out, "rg"), None);
+    }
+
     #[test]
     fn test_capabilities() {
         let provider = CargoProvider::new();
@@ -733,6 +843,30 @@ fn test_is_crate_installed_exact_match() {
         assert!(!is_crate_in_installed_output(output, "serde_yaml"));
     }
 
+    #[test]
+    fn test_parse_installed_binaries() {
+        let output = "\
+ripgrep v14.1.0:
+    rg
+cargo-edit v0.12.2:
+    cargo-add
+    cargo-rm
+serde v1.0.200:
+";
+
+        let (version, binaries) = parse_installed_binaries(output, "cargo-edit").unwrap();
+        assert_eq!(version, "0.12.2");
+        assert_eq!(binaries, vec!["cargo-add", "cargo-rm"]);
+
+        let (version, binaries) = parse_installed_binaries(output, "ripgrep").unwrap();
+        assert_eq!(version, "14.1.0");
+        assert_eq!(binaries, vec!["rg"]);
+
+        let (_, binaries) = parse_installed_binaries(output, "serde").unwrap();
+        assert!(binaries.is_empty());
+        assert!(parse_installed_binaries(output, "rg").is_none());
+    }
+
     #[test]
     fn test_get_cargo_home_env_var() {
         // This tests the fallback logic structure, not actual env var
@@ -2,23 +2,48 @@
 use crate::error::{CogniaError, CogniaResult};
 use crate::platform::{env::Platform, process};
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::path::PathBuf;
 use std::time::Duration;
 
 pub struct ChocolateyProvider;
 
+/// Flags appended to every choco invocation so output stays free of progress
+/// bars and color codes regardless of the console.
+const COMMON_FLAGS: &[&str] = &["--no-progress", "--no-color"];
+
+/// A package source from `choco source list -r`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChocoSource {
+    pub name: String,
+    pub url: String,
+    pub disabled: bool,
+    pub priority: i32,
+}
+
+/// A pinned package from `choco pin list -r`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChocoPin {
+    pub name: String,
+    pub version: String,
+}
+
 impl ChocolateyProvider {
     pub fn new() -> Self {
         Self
     }
 
     /// Execute a choco command with common flags and timeout.
-    /// Always passes `--no-progress` to suppress progress bars in automated output.
+    /// Always passes [`COMMON_FLAGS`] to keep automated output plain.
     async fn run_choco(&self, args: &[&str]) -> CogniaResult<String> {
         let mut full_args: Vec<&str> = args.to_vec();
-        if !full_args.contains(&"--no-progress") {
-            full_args.push("--no-progress");
+        for flag in COMMON_FLAGS {
+            if !full_args.contains(flag) {
+                full_args.push(flag);
+            }
         }
 
         let opts = process::ProcessOptions::new().with_timeout(Duration::from_secs(300)); // choco operations can be slow
@@ -105,6 +130,94 @@ fn parse_outdated_output(output: &str) -> Vec<(&str, &str, &str, bool)> {
             })
             .collect()
     }
+
+    /// Parse `choco source list -r` format:
+    /// `name|url|disabled|user|certificate|priority|...` per line.
+    fn parse_source_list(output: &str) -> Vec<ChocoSource> {
+        output
+            .lines()
+            .filter_map(|line| {
+                let parts: Vec<&str> = line.trim().split('|').map(str::trim).collect();
+                if parts.len() < 2 || parts[0].is_empty() {
+                    return None;
+                }
+                Some(ChocoSource {
+                    name: parts[0].to_string(),
+                    url: parts[1].to_string(),
+                    disabled: parts.get(2).is_some_and(|d| d.eq_ignore_ascii_case("true")),
+                    priority: parts.get(5).and_then(|p| p.parse().ok()).unwrap_or(0),
+                })
+            })
+            .collect()
+    }
+
+    // ── Source management ──────────────────────────────────────────────
+
+    /// List configured package sources.
+    pub async fn source_list(&self) -> CogniaResult<Vec<ChocoSource>> {
+        let out = self.run_choco(&["source", "list", "-r"]).await?;
+        Ok(Self::parse_source_list(&out))
+    }
+
+    /// Add a package source. Lower `priority` values win; 0 means unordered.
+    pub async fn source_add(
+        &self,
+        name: &str,
+        url: &str,
+        priority: Option<i32>,
+    ) -> CogniaResult<()> {
+        let name_arg = format!("--name={}", name);
+        let source_arg = format!("--source={}", url);
+        let mut args = vec!["source", "add", &*name_arg, &*source_arg];
+        let priority_arg;
+        if let Some(priority) = priority {
+            priority_arg = format!("--priority={}", priority);
+            args.push(&priority_arg);
+        }
+        self.run_choco(&args).await?;
+        Ok(())
+    }
+
+    /// Remove a package source.
+    pub async fn source_remove(&self, name: &str) -> CogniaResult<()> {
+        let name_arg = format!("--name={}", name);
+        self.run_choco(&["source", "remove", &name_arg]).await?;
+        Ok(())
+    }
+
+    // ── Pin management ─────────────────────────────────────────────────
+
+    /// List pinned packages.
+    pub async fn pin_list(&self) -> CogniaResult<Vec<ChocoPin>> {
+        let out = self.run_choco(&["pin", "list", "-r"]).await?;
+        Ok(Self::parse_pipe_output(&out)
+            .into_iter()
+            .map(|(name, version)| ChocoPin {
+                name: name.into(),
+                version: version.into(),
+            })
+            .collect())
+    }
+
+    /// Pin a package at `version`, or at its installed version when `None`.
+    pub async fn pin_add(&self, name: &str, version: Option<&str>) -> CogniaResult<()> {
+        let name_arg = format!("--name={}", name);
+        let mut args = vec!["pin", "add", &*name_arg];
+        let version_arg;
+        if let Some(version) = version {
+            version_arg = format!("--version={}", version);
+            args.push(&version_arg);
+        }
+        self.run_choco(&args).await?;
+        Ok(())
+    }
+
+    /// Remove the pin from a package.
+    pub async fn pin_remove(&self, name: &str) -> CogniaResult<()> {
+        let name_arg = format!("--name={}", name);
+        self.run_choco(&["pin", "remove", &name_arg]).await?;
+        Ok(())
+    }
 }
 
 impl Default for ChocolateyProvider {
@@ -129,6 +242,9 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::List,
             Capability::Update,
             Capability::Upgrade,
+            Capability::Info,
+            Capability::Versions,
+            Capability::RequiresElevation,
         ])
     }
     fn supported_platforms(&self) -> Vec<Platform> {
@@ -373,6 +489,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                     install_path,
                     installed_at: String::new(),
                     is_global: true,
+                    kind: None,
                 }
             })
             .collect())
@@ -407,6 +524,37 @@ async fn check_updates(&self, packages: &[String]) -> CogniaResult<Vec<UpdateInf
             })
             .collect())
     }
+
+    async fn health_metrics(&self) -> Vec<ProviderHealthMetric> {
+        let (sources, outdated) = tokio::join!(
+            self.run_choco(&["source", "list", "-r"]),
+            self.run_choco(&["outdated", "-r", "--ignore-unfound"])
+        );
+
+        let mut metrics = Vec::new();
+        if let Ok(out) = sources {
+            let sources = Self::parse_source_list(&out);
+            metrics.push(ProviderHealthMetric::new(
+                "sources",
+                "Sources",
+                sources.iter().filter(|s| !s.disabled).count() as u64,
+            ));
+        }
+        if let Ok(out) = outdated {
+            let parsed = Self::parse_outdated_output(&out);
+            metrics.push(ProviderHealthMetric::new(
+                "outdated",
+                "Outdated packages",
+                parsed.iter().filter(|(.., pinned)| !pinned).count() as u64,
+            ));
+            metrics.push(ProviderHealthMetric::new(
+                "pinned",
+                "Pinned packages",
+                parsed.iter().filter(|(.., pinned)| *pinned).count() as u64,
+            ));
+        }
+        metrics
+    }
 }
 
 #[async_trait]
@@ -416,7 +564,18 @@ async fn check_system_requirements(&self) -> CogniaResult<bool> {
     }
 
     fn requires_elevation(&self, operation: &str) -> bool {
-        matches!(operation, "install" | "uninstall" | "update" | "upgrade")
+        // Chocolatey writes to ProgramData, so anything that changes state needs admin
+        matches!(
+            operation,
+            "install"
+                | "uninstall"
+                | "update"
+                | "upgrade"
+                | "source_add"
+                | "source_remove"
+                | "pin_add"
+                | "pin_remove"
+        )
     }
 
     async fn get_version(&self) -> CogniaResult<String> {
@@ -580,6 +739,49 @@ fn test_requires_elevation() {
         assert!(!SystemPackageProvider::requires_elevation(
             &provider, "list"
         ));
+        assert_eq!(
+            provider.elevated_operations(),
+            vec![
+                "install",
+                "uninstall",
+                "upgrade",
+                "source_add",
+                "source_remove",
+                "pin_add",
+                "pin_remove"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_source_list() {
+        let output = "chocolatey|https://community.chocolatey.org/api/v2/|False|||0|False|False|False\ninternal|https://nexus.example.com/repository/choco/|True|ci-user||1|False|False|True\n";
+        let sources = ChocolateyProvider::parse_source_list(output);
+        assert_eq!(
+            sources,
+            vec![
+                ChocoSource {
+                    name: "chocolatey".into(),
+                    url: "https://community.chocolatey.org/api/v2/".into(),
+                    disabled: false,
+                    priority: 0,
+                },
+                ChocoSource {
+                    name: "internal".into(),
+                    url: "https://nexus.example.com/repository/choco/".into(),
+                    disabled: true,
+                    priority: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_source_list_ignores_noise() {
+        let output = "\nChocolatey v2.2.2\nchocolatey|https://community.chocolatey.org/api/v2/\n";
+        let sources = ChocolateyProvider::parse_source_list(output);
+        assert_eq!(sources.len(), 1);
+        assert!(!sources[0].disabled);
     }
 
     #[test]
@@ -24,7 +24,7 @@ pub struct ComposerProvider {
 impl ComposerProvider {
     pub fn new() -> Self {
         Self {
-            client: crate::platform::proxy::get_shared_client(),
+            client: crate::platform::proxy::get_client_for_provider("composer"),
         }
     }
 
@@ -183,6 +183,7 @@ fn parse_composer_show_output(output: &str) -> Vec<InstalledPackage> {
                     install_path: PathBuf::from("vendor").join(parts[0]),
                     installed_at: String::new(),
                     is_global: false,
+                    kind: None,
                 });
             }
         }
@@ -280,6 +281,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::List,
             Capability::Update,
             Capability::LockVersion,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
 
@@ -376,7 +379,7 @@ async fn get_versions(&self, name: &str) -> CogniaResult<Vec<VersionInfo>> {
     async fn get_dependencies(&self, name: &str, _version: &str) -> CogniaResult<Vec<Dependency>> {
         // Use Packagist API to get package dependencies
         let url = format!("https://repo.packagist.org/p2/{}.json", name);
-        let client = crate::platform::proxy::get_shared_client();
+        let client = crate::platform::proxy::get_client_for_provider("composer");
 
         if let Ok(resp) = client
             .get(&url)
@@ -483,6 +486,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                             install_path,
                             installed_at: String::new(),
                             is_global: filter.global_only,
+                            kind: None,
                         }
                     })
                     .collect();
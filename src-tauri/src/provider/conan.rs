@@ -220,6 +220,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::Search,
             Capability::List,
             Capability::Update,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
     fn supported_platforms(&self) -> Vec<Platform> {
@@ -596,6 +598,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                     install_path: install_path.clone(),
                     installed_at: String::new(),
                     is_global: true,
+                    kind: None,
                 })
             })
             .collect())
@@ -121,6 +121,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::List,
             Capability::Update,
             Capability::LockVersion,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
     fn supported_platforms(&self) -> Vec<Platform> {
@@ -374,6 +376,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                             install_path: prefix.clone(),
                             installed_at: String::new(),
                             is_global: true,
+                            kind: None,
                         })
                     })
                     .collect();
@@ -0,0 +1,505 @@
+//! CUDA toolkit and NVIDIA driver discovery.
+//!
+//! Toolkits are found through `nvcc` on PATH, the versioned install
+//! directories (`/usr/local/cuda-*`, `/opt/cuda`) and the `CUDA_PATH*`
+//! variables set by the Windows installer. The driver side comes from
+//! `nvidia-smi`, which reports both the driver version and the newest CUDA
+//! version that driver can run.
+
+use crate::platform::process::{self, ProcessOptions};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+static NVCC_FULL_VERSION_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\bV(\d+\.\d+\.\d+)").expect("valid regex"));
+static NVCC_RELEASE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"release (\d+\.\d+)").expect("valid regex"));
+static SMI_DRIVER_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"Driver Version:\s*([\d.]+)").expect("valid regex"));
+static SMI_CUDA_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"CUDA Version:\s*([\d.]+)").expect("valid regex"));
+static VERSION_TXT_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"CUDA Version (\d+\.\d+(?:\.\d+)?)").expect("valid regex"));
+static DIR_VERSION_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^cuda-(\d+\.\d+)$").expect("valid regex"));
+static ENV_VERSION_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^CUDA_PATH_V(\d+)_(\d+)$").expect("valid regex"));
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Minimum driver version for each toolkit release, as published in the CUDA
+/// toolkit release notes: `(toolkit, linux driver, windows driver)`.
+const DRIVER_REQUIREMENTS: &[(&str, &str, &str)] = &[
+    ("12.8", "570.26", "570.65"),
+    ("12.6", "560.28.03", "560.76"),
+    ("12.5", "555.42.02", "555.85"),
+    ("12.4", "550.54.14", "551.61"),
+    ("12.3", "545.23.06", "545.84"),
+    ("12.2", "535.54.03", "536.25"),
+    ("12.1", "530.30.02", "531.14"),
+    ("12.0", "525.60.13", "527.41"),
+    ("11.8", "520.61.05", "520.06"),
+    ("11.7", "515.43.04", "516.01"),
+    ("11.6", "510.39.01", "511.23"),
+    ("11.5", "495.29.05", "496.04"),
+    ("11.4", "470.42.01", "471.11"),
+    ("11.3", "465.19.01", "465.89"),
+    ("11.2", "460.27.03", "460.82"),
+    ("11.1", "455.23", "456.38"),
+    ("11.0", "450.36.06", "451.22"),
+    ("10.2", "440.33", "441.22"),
+    ("10.1", "418.39", "418.96"),
+    ("10.0", "410.48", "411.31"),
+];
+
+/// An installed CUDA toolkit
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CudaToolkit {
+    pub version: String,
+    pub path: PathBuf,
+    /// Where the toolkit was found: `nvcc`, `directory` or `env`
+    pub source: String,
+    /// The toolkit whose `nvcc` is first on PATH
+    pub is_current: bool,
+}
+
+/// The NVIDIA driver as reported by `nvidia-smi`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NvidiaDriverInfo {
+    pub driver_version: String,
+    /// Newest CUDA version the driver supports
+    pub max_cuda_version: Option<String>,
+}
+
+/// Whether the installed driver can run a given CUDA toolkit version
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CudaCompatibility {
+    pub requested_version: String,
+    pub driver: Option<NvidiaDriverInfo>,
+    /// Minimum driver version for the requested toolkit, when known
+    pub required_driver_version: Option<String>,
+    pub compatible: bool,
+    pub message: String,
+}
+
+fn version_parts(version: &str) -> Vec<u64> {
+    version
+        .trim()
+        .trim_start_matches('v')
+        .split('.')
+        .map_while(|part| part.parse().ok())
+        .collect()
+}
+
+/// Compare dotted numeric versions, treating missing components as zero
+pub fn compare_versions(left: &str, right: &str) -> Ordering {
+    let left = version_parts(left);
+    let right = version_parts(right);
+    for index in 0..left.len().max(right.len()) {
+        let ordering = left
+            .get(index)
+            .unwrap_or(&0)
+            .cmp(right.get(index).unwrap_or(&0));
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+/// `major.minor` of a toolkit version, e.g. `12.4.131` -> `12.4`
+pub fn release_of(version: &str) -> Option<String> {
+    let parts = version_parts(version);
+    match parts.as_slice() {
+        [major, minor, ..] => Some(format!("{}.{}", major, minor)),
+        [major] => Some(format!("{}.0", major)),
+        [] => None,
+    }
+}
+
+/// Parse `nvcc --version`, preferring the full `V12.4.131` build version
+pub fn parse_nvcc_version(output: &str) -> Option<String> {
+    NVCC_FULL_VERSION_RE
+        .captures(output)
+        .or_else(|| NVCC_RELEASE_RE.captures(output))
+        .and_then(|caps| caps.get(1))
+        .map(|m| m.as_str().to_string())
+}
+
+/// Parse the banner printed by a bare `nvidia-smi`
+pub fn parse_nvidia_smi(output: &str) -> Option<NvidiaDriverInfo> {
+    let driver_version = SMI_DRIVER_RE.captures(output)?.get(1)?.as_str().to_string();
+    let max_cuda_version = SMI_CUDA_RE
+        .captures(output)
+        .and_then(|caps| caps.get(1))
+        .map(|m| m.as_str().to_string());
+    Some(NvidiaDriverInfo {
+        driver_version,
+        max_cuda_version,
+    })
+}
+
+/// Toolkit version recorded inside an install directory. CUDA 11.1+ ships
+/// `version.json`, older releases `version.txt`.
+fn parse_toolkit_version_files(
+    version_json: Option<&str>,
+    version_txt: Option<&str>,
+) -> Option<String> {
+    if let Some(json) = version_json {
+        if let Ok(value) = serde_json::from_str::<serde_json::Value>(json) {
+            if let Some(version) = value
+                .get("cuda")
+                .and_then(|cuda| cuda.get("version"))
+                .and_then(|version| version.as_str())
+            {
+                return Some(version.to_string());
+            }
+        }
+    }
+    version_txt
+        .and_then(|txt| VERSION_TXT_RE.captures(txt))
+        .and_then(|caps| caps.get(1))
+        .map(|m| m.as_str().to_string())
+}
+
+/// Minimum driver for a toolkit release, `None` when the release is not in
+/// the table
+pub fn required_driver_version(toolkit_version: &str, windows: bool) -> Option<&'static str> {
+    let release = release_of(toolkit_version)?;
+    DRIVER_REQUIREMENTS
+        .iter()
+        .find(|(toolkit, _, _)| *toolkit == release)
+        .map(|(_, linux, win)| if windows { *win } else { *linux })
+}
+
+/// Check a requested toolkit version against the installed driver. The
+/// driver's own CUDA ceiling is authoritative; the release-notes table adds
+/// the exact minimum driver where it is known.
+pub fn check_compatibility(
+    requested_version: &str,
+    driver: Option<&NvidiaDriverInfo>,
+    windows: bool,
+) -> CudaCompatibility {
+    let requested = requested_version.trim().trim_start_matches('v');
+    let required = required_driver_version(requested, windows).map(str::to_string);
+    let mut result = CudaCompatibility {
+        requested_version: requested.to_string(),
+        driver: driver.cloned(),
+        required_driver_version: required.clone(),
+        compatible: false,
+        message: String::new(),
+    };
+
+    let Some(driver) = driver else {
+        result.message = "No NVIDIA driver detected (nvidia-smi not found or failed)".into();
+        return result;
+    };
+    let Some(release) = release_of(requested) else {
+        result.message = format!("'{}' is not a valid CUDA version", requested);
+        return result;
+    };
+
+    if let Some(max_cuda) = &driver.max_cuda_version {
+        if compare_versions(&release, max_cuda) == Ordering::Greater {
+            result.message = format!(
+                "Driver {} supports CUDA up to {}, but {} was requested",
+                driver.driver_version, max_cuda, release
+            );
+            return result;
+        }
+    }
+    if let Some(required) = &required {
+        if compare_versions(&driver.driver_version, required) == Ordering::Less {
+            result.message = format!(
+                "CUDA {} requires driver {} or newer, found {}",
+                release, required, driver.driver_version
+            );
+            return result;
+        }
+    }
+
+    result.compatible = true;
+    result.message = match (&driver.max_cuda_version, &required) {
+        (None, None) => format!(
+            "Driver {} reports no CUDA ceiling and CUDA {} is not in the driver table; compatibility is assumed",
+            driver.driver_version, release
+        ),
+        _ => format!(
+            "Driver {} supports CUDA {}",
+            driver.driver_version, release
+        ),
+    };
+    result
+}
+
+/// Query `nvidia-smi` for the active driver
+pub async fn detect_driver() -> Option<NvidiaDriverInfo> {
+    let options = ProcessOptions::new().with_timeout(PROBE_TIMEOUT);
+    let output = process::execute("nvidia-smi", &[], Some(options))
+        .await
+        .ok()?;
+    if !output.success {
+        return None;
+    }
+    parse_nvidia_smi(&output.stdout)
+}
+
+async fn read_toolkit_dir_version(dir: &Path) -> Option<String> {
+    let json = tokio::fs::read_to_string(dir.join("version.json"))
+        .await
+        .ok();
+    let txt = tokio::fs::read_to_string(dir.join("version.txt"))
+        .await
+        .ok();
+    parse_toolkit_version_files(json.as_deref(), txt.as_deref())
+}
+
+/// Toolkit root for an `nvcc` executable (`<root>/bin/nvcc`)
+fn toolkit_root_for_nvcc(nvcc: &Path) -> PathBuf {
+    nvcc.parent()
+        .and_then(Path::parent)
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| nvcc.to_path_buf())
+}
+
+async fn current_toolkit() -> Option<CudaToolkit> {
+    let nvcc = process::which("nvcc").await?;
+    let options = ProcessOptions::new().with_timeout(PROBE_TIMEOUT);
+    let output = process::execute("nvcc", &["--version"], Some(options))
+        .await
+        .ok()?;
+    if !output.success {
+        return None;
+    }
+    let version = parse_nvcc_version(&output.stdout)?;
+    let path = toolkit_root_for_nvcc(Path::new(&nvcc));
+    Some(CudaToolkit {
+        version,
+        path: std::fs::canonicalize(&path).unwrap_or(path),
+        source: "nvcc".into(),
+        is_current: true,
+    })
+}
+
+async fn directory_toolkits() -> Vec<CudaToolkit> {
+    let mut found = Vec::new();
+    if cfg!(windows) {
+        return found;
+    }
+
+    if let Ok(mut entries) = tokio::fs::read_dir("/usr/local").await {
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Some(caps) = DIR_VERSION_RE.captures(&name) else {
+                continue;
+            };
+            let path = entry.path();
+            let version = read_toolkit_dir_version(&path)
+                .await
+                .unwrap_or_else(|| caps[1].to_string());
+            found.push(CudaToolkit {
+                version,
+                path: std::fs::canonicalize(&path).unwrap_or(path),
+                source: "directory".into(),
+                is_current: false,
+            });
+        }
+    }
+
+    // Arch and some other distributions install a single unversioned toolkit
+    for root in ["/usr/local/cuda", "/opt/cuda"] {
+        let path = PathBuf::from(root);
+        if let Some(version) = read_toolkit_dir_version(&path).await {
+            found.push(CudaToolkit {
+                version,
+                path: std::fs::canonicalize(&path).unwrap_or(path),
+                source: "directory".into(),
+                is_current: false,
+            });
+        }
+    }
+    found
+}
+
+/// Toolkits registered through `CUDA_PATH_V<major>_<minor>` variables
+fn toolkits_from_env_vars(vars: impl IntoIterator<Item = (String, String)>) -> Vec<CudaToolkit> {
+    let mut found: Vec<CudaToolkit> = vars
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let caps = ENV_VERSION_RE.captures(&key)?;
+            Some(CudaToolkit {
+                version: format!("{}.{}", &caps[1], &caps[2]),
+                path: PathBuf::from(value),
+                source: "env".into(),
+                is_current: false,
+            })
+        })
+        .collect();
+    found.sort_by(|a, b| compare_versions(&b.version, &a.version));
+    found
+}
+
+/// Merge toolkits found by different probes. Entries pointing at the same
+/// directory are collapsed, keeping the most precise version string.
+fn merge_toolkits(candidates: Vec<CudaToolkit>) -> Vec<CudaToolkit> {
+    let mut merged: Vec<CudaToolkit> = Vec::new();
+    for toolkit in candidates {
+        if let Some(existing) = merged.iter_mut().find(|existing| {
+            existing.path == toolkit.path
+                || (release_of(&existing.version) == release_of(&toolkit.version)
+                    && (existing.source == "env" || toolkit.source == "env"))
+        }) {
+            existing.is_current |= toolkit.is_current;
+            if version_parts(&toolkit.version).len() > version_parts(&existing.version).len() {
+                existing.version = toolkit.version;
+            }
+            continue;
+        }
+        merged.push(toolkit);
+    }
+    merged.sort_by(|a, b| compare_versions(&b.version, &a.version));
+    merged
+}
+
+/// Every CUDA toolkit found on this machine, newest first
+pub async fn list_installed_toolkits() -> Vec<CudaToolkit> {
+    let mut candidates = Vec::new();
+    if let Some(current) = current_toolkit().await {
+        candidates.push(current);
+    }
+    candidates.extend(directory_toolkits().await);
+    candidates.extend(toolkits_from_env_vars(std::env::vars()));
+    merge_toolkits(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nvcc_version() {
+        let output = "nvcc: NVIDIA (R) Cuda compiler driver\n\
+            Copyright (c) 2005-2024 NVIDIA Corporation\n\
+            Built on Thu_Mar_28_02:18:24_PDT_2024\n\
+            Cuda compilation tools, release 12.4, V12.4.131\n\
+            Build cuda_12.4.r12.4/compiler.34097967_0";
+        assert_eq!(parse_nvcc_version(output), Some("12.4.131".into()));
+        assert_eq!(
+            parse_nvcc_version("Cuda compilation tools, release 9.2"),
+            Some("9.2".into())
+        );
+        assert_eq!(parse_nvcc_version("command not found"), None);
+    }
+
+    #[test]
+    fn test_parse_nvidia_smi() {
+        let output = "+-----------------------------------------------------------------------------------------+\n\
+            | NVIDIA-SMI 550.54.14              Driver Version: 550.54.14      CUDA Version: 12.4     |\n\
+            |-----------------------------------------+------------------------+----------------------+";
+        let info = parse_nvidia_smi(output).unwrap();
+        assert_eq!(info.driver_version, "550.54.14");
+        assert_eq!(info.max_cuda_version.as_deref(), Some("12.4"));
+        assert!(parse_nvidia_smi("NVIDIA-SMI has failed").is_none());
+    }
+
+    #[test]
+    fn test_parse_toolkit_version_files() {
+        let json = r#"{"cuda": {"name": "CUDA SDK", "version": "11.8.20220929"}}"#;
+        assert_eq!(
+            parse_toolkit_version_files(Some(json), None),
+            Some("11.8.20220929".into())
+        );
+        assert_eq!(
+            parse_toolkit_version_files(None, Some("CUDA Version 10.2.89\n")),
+            Some("10.2.89".into())
+        );
+        assert_eq!(parse_toolkit_version_files(Some("{}"), None), None);
+    }
+
+    #[test]
+    fn test_toolkits_from_env_vars() {
+        let found = toolkits_from_env_vars([
+            ("CUDA_PATH".to_string(), "C:\\CUDA\\v12.4".to_string()),
+            ("CUDA_PATH_V11_8".to_string(), "C:\\CUDA\\v11.8".to_string()),
+            ("CUDA_PATH_V12_4".to_string(), "C:\\CUDA\\v12.4".to_string()),
+            ("PATH".to_string(), "C:\\Windows".to_string()),
+        ]);
+        let versions: Vec<&str> = found.iter().map(|t| t.version.as_str()).collect();
+        assert_eq!(versions, ["12.4", "11.8"]);
+    }
+
+    #[test]
+    fn test_merge_prefers_precise_version_and_keeps_current() {
+        let merged = merge_toolkits(vec![
+            CudaToolkit {
+                version: "12.4.131".into(),
+                path: PathBuf::from("/usr/local/cuda-12.4"),
+                source: "nvcc".into(),
+                is_current: true,
+            },
+            CudaToolkit {
+                version: "12.4".into(),
+                path: PathBuf::from("/usr/local/cuda-12.4"),
+                source: "directory".into(),
+                is_current: false,
+            },
+            CudaToolkit {
+                version: "11.8.0".into(),
+                path: PathBuf::from("/usr/local/cuda-11.8"),
+                source: "directory".into(),
+                is_current: false,
+            },
+        ]);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].version, "12.4.131");
+        assert!(merged[0].is_current);
+        assert_eq!(merged[1].version, "11.8.0");
+    }
+
+    #[test]
+    fn test_compatible_within_driver_ceiling() {
+        let driver = NvidiaDriverInfo {
+            driver_version: "550.54.14".into(),
+            max_cuda_version: Some("12.4".into()),
+        };
+        let result = check_compatibility("12.2", Some(&driver), false);
+        assert!(result.compatible, "{}", result.message);
+        assert_eq!(result.required_driver_version.as_deref(), Some("535.54.03"));
+    }
+
+    #[test]
+    fn test_incompatible_above_driver_ceiling() {
+        let driver = NvidiaDriverInfo {
+            driver_version: "535.54.03".into(),
+            max_cuda_version: Some("12.2".into()),
+        };
+        let result = check_compatibility("12.4.1", Some(&driver), false);
+        assert!(!result.compatible);
+        assert!(result.message.contains("up to 12.2"));
+    }
+
+    #[test]
+    fn test_incompatible_below_table_minimum() {
+        let driver = NvidiaDriverInfo {
+            driver_version: "550.40".into(),
+            max_cuda_version: None,
+        };
+        let result = check_compatibility("12.4", Some(&driver), true);
+        assert!(!result.compatible);
+        assert_eq!(result.required_driver_version.as_deref(), Some("551.61"));
+    }
+
+    #[test]
+    fn test_no_driver_is_incompatible() {
+        let result = check_compatibility("12.4", None, false);
+        assert!(!result.compatible);
+        assert!(result.driver.is_none());
+    }
+}
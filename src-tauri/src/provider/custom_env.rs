@@ -0,0 +1,883 @@
+use super::github::GitHubProvider;
+use super::traits::*;
+use crate::core::custom_detection::CustomDetectionManager;
+use crate::core::custom_env_type::{
+    validate_version, CommandTemplate, CustomEnvTypeDefinition, VersionListSource,
+};
+use crate::core::shim::{ShimConfig, ShimManager};
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::{
+    env::{EnvModifications, Platform},
+    process::{self, ProcessOptions},
+};
+use crate::resolver::Version;
+use async_trait::async_trait;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+const VERSION_LIST_TIMEOUT: Duration = Duration::from_secs(30);
+const INSTALL_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Environment provider for a user-defined environment type.
+///
+/// Versions are installed by the type's install command into
+/// `<root>/versions/<type id>/<version>` and the global version is a
+/// `current` link next to them. The type's binaries get direct shims to the
+/// global version.
+pub struct CustomEnvProvider {
+    definition: CustomEnvTypeDefinition,
+    provider_id: String,
+    version_file: String,
+    root_dir: PathBuf,
+    versions_dir: PathBuf,
+    detection: Arc<RwLock<CustomDetectionManager>>,
+}
+
+impl CustomEnvProvider {
+    pub fn new(
+        definition: CustomEnvTypeDefinition,
+        root_dir: &Path,
+        detection: Arc<RwLock<CustomDetectionManager>>,
+    ) -> Self {
+        Self {
+            provider_id: definition.provider_id(),
+            version_file: format!(".{}-version", definition.id),
+            root_dir: root_dir.to_path_buf(),
+            versions_dir: root_dir.join("versions").join(&definition.id),
+            definition,
+            detection,
+        }
+    }
+
+    fn current_link(&self) -> PathBuf {
+        self.versions_dir.join("current")
+    }
+
+    /// Directory of `version`, refused unless it is a direct child of the
+    /// versions dir; install and uninstall delete it recursively
+    fn version_dir(&self, version: &str) -> CogniaResult<PathBuf> {
+        validate_version(version)?;
+        let dir = self.versions_dir.join(version);
+        if dir.parent() != Some(self.versions_dir.as_path()) || !dir.starts_with(&self.versions_dir)
+        {
+            return Err(CogniaError::Provider(format!(
+                "Invalid version '{}': resolves outside {}",
+                version,
+                self.versions_dir.display()
+            )));
+        }
+        Ok(dir)
+    }
+
+    fn scan_installed_versions(&self) -> Vec<(String, PathBuf)> {
+        let Ok(entries) = std::fs::read_dir(&self.versions_dir) else {
+            return Vec::new();
+        };
+        let mut installed: Vec<(String, PathBuf)> = entries
+            .flatten()
+            .filter(|entry| entry.file_type().map(|t| t.is_dir()).unwrap_or(false))
+            .map(|entry| {
+                (
+                    entry.file_name().to_string_lossy().to_string(),
+                    entry.path(),
+                )
+            })
+            .filter(|(name, _)| name != "current" && !name.starts_with('.'))
+            .collect();
+        installed.sort_by(|(a, _), (b, _)| compare_versions_desc(a, b));
+        installed
+    }
+
+    fn dir_installed_at(path: &Path) -> Option<String> {
+        std::fs::metadata(path)
+            .ok()
+            .and_then(|m| m.modified().ok())
+            .map(|t| {
+                let dt: chrono::DateTime<chrono::Utc> = t.into();
+                dt.to_rfc3339()
+            })
+    }
+
+    fn remove_current_link(current_link: &Path) {
+        if !(current_link.exists() || current_link.is_symlink()) {
+            return;
+        }
+        #[cfg(windows)]
+        {
+            if current_link.is_dir() {
+                let _ = std::fs::remove_dir(current_link);
+            } else {
+                let _ = std::fs::remove_file(current_link);
+            }
+        }
+        #[cfg(not(windows))]
+        {
+            let _ = std::fs::remove_file(current_link);
+        }
+    }
+
+    /// Path of `binary` in an installed version, looked up in `bin/` first
+    fn binary_path(version_path: &Path, binary: &str) -> Option<PathBuf> {
+        let mut names = vec![binary.to_string()];
+        if cfg!(windows) {
+            names.insert(0, format!("{}.exe", binary));
+            names.push(format!("{}.cmd", binary));
+        }
+        [version_path.join("bin"), version_path.to_path_buf()]
+            .iter()
+            .flat_map(|dir| names.iter().map(move |name| dir.join(name)))
+            .find(|path| path.is_file())
+    }
+
+    /// Point a direct shim for each of the type's binaries at `version`, or
+    /// drop them when there is no global version left. Shims owned by another
+    /// environment type are left alone.
+    async fn sync_shims(&self, version: Option<&str>) -> CogniaResult<()> {
+        if self.definition.binaries.is_empty() {
+            return Ok(());
+        }
+        let mut shims = ShimManager::new(&self.root_dir).await?;
+        for binary in &self.definition.binaries {
+            if shims
+                .get_shim(binary)
+                .is_some_and(|config| config.env_type != self.definition.id)
+            {
+                log::warn!(
+                    "Not shimming {} for {}: the shim belongs to another environment",
+                    binary,
+                    self.definition.display_name
+                );
+                continue;
+            }
+            let target = match version {
+                Some(version) => Self::binary_path(&self.version_dir(version)?, binary),
+                None => None,
+            };
+            if let (Some(version), Some(target_path)) = (version, target) {
+                shims
+                    .create_shim(ShimConfig {
+                        env_type: self.definition.id.clone(),
+                        binary_name: binary.clone(),
+                        version: Some(version.to_string()),
+                        target_path,
+                        direct: true,
+                        version_switch: false,
+                    })
+                    .await?;
+                continue;
+            }
+            if let Some(version) = version {
+                log::warn!(
+                    "{} {} has no '{}' binary to shim",
+                    self.definition.display_name,
+                    version,
+                    binary
+                );
+            }
+            if shims.get_shim(binary).is_some() {
+                shims.remove_shim(binary).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Versions from the definition's source, newest first as the source lists them
+    async fn fetch_available_versions(&self) -> CogniaResult<Vec<VersionInfo>> {
+        let versions = match &self.definition.version_source {
+            VersionListSource::Static { versions } => versions
+                .iter()
+                .map(|v| v.trim())
+                .filter(|v| !v.is_empty())
+                .map(|v| VersionInfo {
+                    version: v.to_string(),
+                    release_date: None,
+                    deprecated: false,
+                    yanked: false,
+                })
+                .collect(),
+            VersionListSource::GithubReleases {
+                repo,
+                tag_prefix,
+                include_prereleases,
+            } => GitHubProvider::new()
+                .list_releases(repo)
+                .await?
+                .into_iter()
+                .filter(|release| !release.draft && (*include_prereleases || !release.prerelease))
+                .map(|release| VersionInfo {
+                    version: release_version(&release.tag_name, tag_prefix.as_deref()),
+                    release_date: release.published_at,
+                    deprecated: false,
+                    yanked: false,
+                })
+                .collect(),
+            VersionListSource::Command {
+                command,
+                args,
+                regex,
+            } => {
+                self.definition.ensure_command_permitted(command, args)?;
+                let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+                let output = process::execute(
+                    command,
+                    &arg_refs,
+                    Some(ProcessOptions {
+                        timeout: Some(VERSION_LIST_TIMEOUT),
+                        ..Default::default()
+                    }),
+                )
+                .await?;
+                if !output.success {
+                    return Err(CogniaError::Provider(format!(
+                        "Version list command for '{}' failed: {}",
+                        self.definition.id,
+                        output.stderr.trim()
+                    )));
+                }
+                let regex = regex
+                    .as_deref()
+                    .map(Regex::new)
+                    .transpose()
+                    .map_err(|e| CogniaError::Config(format!("Invalid regex: {}", e)))?;
+                parse_version_lines(&output.stdout, regex.as_ref())
+                    .into_iter()
+                    .map(|version| VersionInfo {
+                        version,
+                        release_date: None,
+                        deprecated: false,
+                        yanked: false,
+                    })
+                    .collect()
+            }
+        };
+        Ok(versions)
+    }
+
+    async fn latest_version(&self) -> CogniaResult<String> {
+        self.fetch_available_versions()
+            .await?
+            .into_iter()
+            .map(|v| v.version)
+            .next()
+            .ok_or_else(|| {
+                CogniaError::Provider(format!(
+                    "No versions available for {}",
+                    self.definition.display_name
+                ))
+            })
+    }
+
+    /// Run a command template with its placeholders filled in
+    async fn run_template(
+        &self,
+        template: &CommandTemplate,
+        version: &str,
+        install_dir: &Path,
+    ) -> CogniaResult<()> {
+        self.definition
+            .ensure_command_permitted(&template.command, &template.args)?;
+        let (program, args) = template.render(version, install_dir);
+        let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+        let env = HashMap::from([
+            ("COGNIA_VERSION".to_string(), version.to_string()),
+            (
+                "COGNIA_INSTALL_DIR".to_string(),
+                install_dir.to_string_lossy().to_string(),
+            ),
+        ]);
+        let output = process::execute(
+            &program,
+            &arg_refs,
+            Some(ProcessOptions {
+                cwd: Some(install_dir.to_string_lossy().to_string()),
+                env,
+                timeout: Some(INSTALL_TIMEOUT),
+                ..Default::default()
+            }),
+        )
+        .await?;
+        if output.success {
+            Ok(())
+        } else {
+            let detail = if output.stderr.trim().is_empty() {
+                output.stdout.trim()
+            } else {
+                output.stderr.trim()
+            };
+            Err(CogniaError::Installation(format!(
+                "'{}' exited with code {}: {}",
+                program, output.exit_code, detail
+            )))
+        }
+    }
+
+    /// Walk up from `start_path` looking for the type's version file
+    async fn find_version_file(&self, start_path: &Path) -> Option<(String, PathBuf)> {
+        let mut current = Some(start_path);
+        while let Some(dir) = current {
+            let candidate = dir.join(&self.version_file);
+            if let Ok(content) = tokio::fs::read_to_string(&candidate).await {
+                let version = content.trim().to_string();
+                if !version.is_empty() {
+                    return Some((version, candidate));
+                }
+            }
+            current = dir.parent();
+        }
+        None
+    }
+}
+
+/// Version from a release tag: the configured prefix, or a leading `v`, is removed
+fn release_version(tag: &str, tag_prefix: Option<&str>) -> String {
+    let stripped = tag_prefix
+        .and_then(|prefix| tag.strip_prefix(prefix))
+        .unwrap_or(tag);
+    stripped
+        .strip_prefix('v')
+        .filter(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+        .unwrap_or(stripped)
+        .to_string()
+}
+
+/// One version per non-empty line, via the regex's `version` or first group
+fn parse_version_lines(stdout: &str, regex: Option<&Regex>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| match regex {
+            Some(regex) => regex.captures(line).and_then(|caps| {
+                caps.name("version")
+                    .or_else(|| caps.get(1))
+                    .or_else(|| caps.get(0))
+                    .map(|m| m.as_str().to_string())
+            }),
+            None => Some(line.to_string()),
+        })
+        .filter(|version| seen.insert(version.clone()))
+        .collect()
+}
+
+fn compare_versions_desc(a: &str, b: &str) -> std::cmp::Ordering {
+    match (a.parse::<Version>(), b.parse::<Version>()) {
+        (Ok(a), Ok(b)) => b.cmp(&a),
+        (Ok(_), Err(_)) => std::cmp::Ordering::Less,
+        (Err(_), Ok(_)) => std::cmp::Ordering::Greater,
+        (Err(_), Err(_)) => b.cmp(a),
+    }
+}
+
+#[async_trait]
+impl Provider for CustomEnvProvider {
+    fn id(&self) -> &str {
+        &self.provider_id
+    }
+
+    fn display_name(&self) -> &str {
+        &self.definition.display_name
+    }
+
+    fn capabilities(&self) -> HashSet<Capability> {
+        HashSet::from([
+            Capability::Install,
+            Capability::Uninstall,
+            Capability::Search,
+            Capability::List,
+            Capability::VersionSwitch,
+            Capability::MultiVersion,
+            Capability::ProjectLocal,
+            Capability::Info,
+            Capability::Versions,
+        ])
+    }
+
+    fn supported_platforms(&self) -> Vec<Platform> {
+        vec![Platform::Windows, Platform::MacOS, Platform::Linux]
+    }
+
+    fn priority(&self) -> i32 {
+        50
+    }
+
+    /// Versions are managed by Cognia itself, so the type is always usable
+    async fn is_available(&self) -> bool {
+        true
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        _options: SearchOptions,
+    ) -> CogniaResult<Vec<PackageSummary>> {
+        let versions = self.fetch_available_versions().await?;
+        Ok(versions
+            .into_iter()
+            .filter(|v| query.is_empty() || v.version.contains(query))
+            .take(20)
+            .map(|v| PackageSummary {
+                name: format!("{}@{}", self.definition.id, v.version),
+                description: self.definition.description.clone(),
+                latest_version: Some(v.version),
+                provider: self.provider_id.clone(),
+            })
+            .collect())
+    }
+
+    async fn get_package_info(&self, name: &str) -> CogniaResult<PackageInfo> {
+        let prefix = format!("{}@", self.definition.id);
+        let version = name.strip_prefix(&prefix).unwrap_or(name);
+        Ok(PackageInfo {
+            name: name.into(),
+            display_name: Some(format!("{} {}", self.definition.display_name, version)),
+            description: self.definition.description.clone(),
+            homepage: None,
+            license: None,
+            repository: match &self.definition.version_source {
+                VersionListSource::GithubReleases { repo, .. } => {
+                    Some(format!("https://github.com/{}", repo))
+                }
+                _ => None,
+            },
+            versions: vec![VersionInfo {
+                version: version.to_string(),
+                release_date: None,
+                deprecated: false,
+                yanked: false,
+            }],
+            provider: self.provider_id.clone(),
+        })
+    }
+
+    async fn get_versions(&self, _name: &str) -> CogniaResult<Vec<VersionInfo>> {
+        self.fetch_available_versions().await
+    }
+
+    async fn get_installed_version(&self, name: &str) -> CogniaResult<Option<String>> {
+        let prefix = format!("{}@", self.definition.id);
+        let version = name.strip_prefix(&prefix).unwrap_or(name);
+        Ok(self
+            .version_dir(version)
+            .is_ok_and(|dir| dir.is_dir())
+            .then(|| version.to_string()))
+    }
+
+    async fn install(&self, req: InstallRequest) -> CogniaResult<InstallReceipt> {
+        let version = match req.version.as_deref() {
+            None | Some("latest") => self.latest_version().await?,
+            Some(version) => version.to_string(),
+        };
+        let install_dir = self.version_dir(&version)?;
+        if install_dir.exists() {
+            if !req.force {
+                return Err(CogniaError::Installation(format!(
+                    "{} {} is already installed",
+                    self.definition.display_name, version
+                )));
+            }
+            tokio::fs::remove_dir_all(&install_dir).await?;
+        }
+        // Checked before anything touches the disk
+        self.definition.ensure_command_permitted(
+            &self.definition.install.command,
+            &self.definition.install.args,
+        )?;
+
+        tokio::fs::create_dir_all(&install_dir).await?;
+        if let Err(e) = self
+            .run_template(&self.definition.install, &version, &install_dir)
+            .await
+        {
+            let _ = tokio::fs::remove_dir_all(&install_dir).await;
+            return Err(e);
+        }
+
+        if self.get_current_version().await?.is_none() {
+            self.set_global_version(&version).await?;
+        }
+
+        Ok(InstallReceipt {
+            name: self.definition.id.clone(),
+            version,
+            provider: self.provider_id.clone(),
+            files: self
+                .definition
+                .binaries
+                .iter()
+                .map(|binary| install_dir.join(binary))
+                .collect(),
+            install_path: install_dir,
+            installed_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    async fn uninstall(&self, req: UninstallRequest) -> CogniaResult<()> {
+        let version = req.version.ok_or_else(|| {
+            CogniaError::Provider(format!(
+                "A version is required to uninstall {}",
+                self.definition.display_name
+            ))
+        })?;
+        let install_dir = self.version_dir(&version)?;
+        if !install_dir.is_dir() {
+            return Err(CogniaError::Provider(format!(
+                "{} {} is not installed",
+                self.definition.display_name, version
+            )));
+        }
+
+        if let Some(uninstall) = &self.definition.uninstall {
+            if let Err(e) = self.run_template(uninstall, &version, &install_dir).await {
+                if !req.force {
+                    return Err(e);
+                }
+                log::warn!("Uninstall command failed, removing anyway: {}", e);
+            }
+        }
+
+        let was_current = self.get_current_version().await?.as_deref() == Some(version.as_str());
+        tokio::fs::remove_dir_all(&install_dir).await?;
+        if was_current {
+            Self::remove_current_link(&self.current_link());
+            match self.scan_installed_versions().into_iter().next() {
+                Some((fallback, _)) => self.set_global_version(&fallback).await?,
+                None => self.sync_shims(None).await?,
+            }
+        }
+        Ok(())
+    }
+
+    async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<InstalledPackage>> {
+        Ok(self
+            .scan_installed_versions()
+            .into_iter()
+            .filter(|(version, _)| match &filter.name_filter {
+                Some(name_filter) => version.contains(name_filter.as_str()),
+                None => true,
+            })
+            .map(|(version, path)| InstalledPackage {
+                name: format!("{}@{}", self.definition.id, version),
+                installed_at: Self::dir_installed_at(&path).unwrap_or_default(),
+                version,
+                provider: self.provider_id.clone(),
+                install_path: path,
+                is_global: true,
+                kind: None,
+            })
+            .collect())
+    }
+
+    async fn check_updates(&self, _packages: &[String]) -> CogniaResult<Vec<UpdateInfo>> {
+        let Some(current) = self.get_current_version().await? else {
+            return Ok(vec![]);
+        };
+        let latest = self.latest_version().await?;
+        if compare_versions_desc(&latest, &current) != std::cmp::Ordering::Less {
+            return Ok(vec![]);
+        }
+        Ok(vec![UpdateInfo {
+            name: format!("{}@{}", self.definition.id, current),
+            current_version: current,
+            latest_version: latest,
+            provider: self.provider_id.clone(),
+        }])
+    }
+}
+
+#[async_trait]
+impl EnvironmentProvider for CustomEnvProvider {
+    async fn list_installed_versions(&self) -> CogniaResult<Vec<InstalledVersion>> {
+        let current = self.get_current_version().await?.unwrap_or_default();
+        Ok(self
+            .scan_installed_versions()
+            .into_iter()
+            .map(|(version, path)| InstalledVersion {
+                is_current: version == current,
+                installed_at: Self::dir_installed_at(&path),
+                version,
+                install_path: path,
+                size: None,
+            })
+            .collect())
+    }
+
+    async fn get_current_version(&self) -> CogniaResult<Option<String>> {
+        let current = self.current_link();
+        if !current.is_dir() {
+            return Ok(None);
+        }
+        Ok(std::fs::read_link(&current)
+            .ok()
+            .and_then(|target| target.file_name().map(|n| n.to_string_lossy().to_string())))
+    }
+
+    async fn set_global_version(&self, version: &str) -> CogniaResult<()> {
+        let version_path = self.version_dir(version)?;
+        if !version_path.is_dir() {
+            return Err(CogniaError::Provider(format!(
+                "{} {} is not installed. Install it first.",
+                self.definition.display_name, version
+            )));
+        }
+
+        let current_link = self.current_link();
+        Self::remove_current_link(&current_link);
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(&version_path, &current_link)
+                .map_err(|e| CogniaError::Io(std::io::Error::other(e.to_string())))?;
+        }
+        #[cfg(windows)]
+        {
+            std::os::windows::fs::symlink_dir(&version_path, &current_link)
+                .map_err(|e| CogniaError::Io(std::io::Error::other(e.to_string())))?;
+        }
+
+        self.sync_shims(Some(version)).await
+    }
+
+    async fn set_local_version(&self, project_path: &Path, version: &str) -> CogniaResult<()> {
+        let version_file = project_path.join(self.version_file_name());
+        crate::platform::fs::write_file_string(&version_file, version).await?;
+        Ok(())
+    }
+
+    async fn detect_version(&self, start_path: &Path) -> CogniaResult<Option<VersionDetection>> {
+        // The type's detection rules take precedence, in the order listed
+        {
+            let detection = self.detection.read().await;
+            for rule_id in &self.definition.detection_rules {
+                let Some(rule) = detection.get_rule(rule_id).filter(|rule| rule.enabled) else {
+                    continue;
+                };
+                match detection.test_rule(rule, start_path).await {
+                    Ok(Some(result)) => {
+                        return Ok(Some(VersionDetection {
+                            version: result.version,
+                            source: VersionSource::LocalFile,
+                            source_path: Some(result.source_file),
+                        }));
+                    }
+                    Ok(None) => {}
+                    // One unusable rule must not hide the others or the version file
+                    Err(e) => log::warn!("Skipping detection rule '{}': {}", rule_id, e),
+                }
+            }
+        }
+
+        if let Some((version, path)) = self.find_version_file(start_path).await {
+            return Ok(Some(VersionDetection {
+                version,
+                source: VersionSource::LocalFile,
+                source_path: Some(path),
+            }));
+        }
+
+        if let Some(version) = self.get_current_version().await? {
+            return Ok(Some(VersionDetection {
+                version,
+                source: VersionSource::SystemDefault,
+                source_path: None,
+            }));
+        }
+
+        Ok(None)
+    }
+
+    fn get_env_modifications(&self, version: &str) -> CogniaResult<EnvModifications> {
+        let version_path = self.version_dir(version)?;
+        let mut mods = EnvModifications::new().prepend_path(&version_path);
+        let bin_dir = version_path.join("bin");
+        if bin_dir.is_dir() {
+            mods = mods.prepend_path(bin_dir);
+        }
+        Ok(mods)
+    }
+
+    fn version_file_name(&self) -> &str {
+        &self.version_file
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::ProviderRegistry;
+
+    fn make_provider(root: &Path) -> CustomEnvProvider {
+        let definition = CustomEnvTypeDefinition {
+            id: "tf".to_string(),
+            display_name: "Terraform".to_string(),
+            description: None,
+            detection_rules: Vec::new(),
+            version_source: VersionListSource::Static {
+                versions: vec!["1.9.0".to_string(), " ".to_string(), "1.8.5".to_string()],
+            },
+            install: CommandTemplate {
+                command: "tf-install".to_string(),
+                args: vec!["{version}".to_string()],
+            },
+            uninstall: None,
+            binaries: vec!["terraform".to_string()],
+            approved_commands: Vec::new(),
+            created_at: None,
+            updated_at: None,
+        };
+        CustomEnvProvider::new(
+            definition,
+            root,
+            Arc::new(RwLock::new(CustomDetectionManager::new(root))),
+        )
+    }
+
+    #[test]
+    fn test_release_version_strips_prefixes() {
+        assert_eq!(release_version("v1.9.0", None), "1.9.0");
+        assert_eq!(release_version("tool-v2.0.1", Some("tool-")), "2.0.1");
+        assert_eq!(release_version("vault", None), "vault");
+    }
+
+    #[test]
+    fn test_parse_version_lines() {
+        let stdout = "kubectl v1.30.1\n\nkubectl v1.29.4\nkubectl v1.30.1\nnoise\n";
+        let regex = Regex::new(r"v(?P<version>\d+\.\d+\.\d+)").unwrap();
+        assert_eq!(
+            parse_version_lines(stdout, Some(&regex)),
+            vec!["1.30.1", "1.29.4"]
+        );
+        assert_eq!(parse_version_lines("1.0\n2.0\n", None), vec!["1.0", "2.0"]);
+    }
+
+    #[tokio::test]
+    async fn test_static_versions_and_installed_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = make_provider(dir.path());
+        assert_eq!(provider.id(), "custom-tf");
+        assert_eq!(provider.version_file_name(), ".tf-version");
+
+        let versions = provider.get_versions("tf").await.unwrap();
+        let versions: Vec<&str> = versions.iter().map(|v| v.version.as_str()).collect();
+        assert_eq!(versions, vec!["1.9.0", "1.8.5"]);
+
+        let versions_dir = dir.path().join("versions").join("tf");
+        std::fs::create_dir_all(versions_dir.join("1.8.5")).unwrap();
+        std::fs::create_dir_all(versions_dir.join("1.10.0")).unwrap();
+        std::fs::create_dir_all(versions_dir.join("1.8.5").join("bin")).unwrap();
+        let binary = versions_dir.join("1.8.5").join("bin").join("terraform");
+        std::fs::write(&binary, "").unwrap();
+        assert_eq!(provider.get_current_version().await.unwrap(), None);
+
+        provider.set_global_version("1.8.5").await.unwrap();
+        let shims = ShimManager::new(dir.path()).await.unwrap();
+        let shim = shims.get_shim("terraform").unwrap();
+        assert!(shim.direct);
+        assert_eq!(shim.env_type, "tf");
+        assert_eq!(shim.version.as_deref(), Some("1.8.5"));
+        assert_eq!(shim.target_path, binary);
+
+        let installed = provider.list_installed_versions().await.unwrap();
+        let installed: Vec<(&str, bool)> = installed
+            .iter()
+            .map(|v| (v.version.as_str(), v.is_current))
+            .collect();
+        assert_eq!(installed, vec![("1.10.0", false), ("1.8.5", true)]);
+        assert!(provider.set_global_version("2.0.0").await.is_err());
+
+        // 1.10.0 ships no terraform binary, so the shim goes away
+        provider.set_global_version("1.10.0").await.unwrap();
+        let shims = ShimManager::new(dir.path()).await.unwrap();
+        assert!(shims.get_shim("terraform").is_none());
+    }
+
+    #[test]
+    fn test_registry_replaces_and_carries_over_custom_providers() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut registry = ProviderRegistry::new();
+        registry.set_custom_environment_providers(vec![Arc::new(make_provider(dir.path()))]);
+        assert_eq!(registry.custom_environment_types(), vec!["tf"]);
+        assert!(registry.get_environment_provider("custom-tf").is_some());
+
+        let mut rebuilt = ProviderRegistry::new();
+        rebuilt.copy_custom_environment_providers(&registry);
+        assert!(rebuilt.get("custom-tf").is_some());
+
+        registry.set_custom_environment_providers(Vec::new());
+        assert!(registry.get("custom-tf").is_none());
+        assert!(registry.custom_environment_types().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_install_requires_approved_command() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = make_provider(dir.path());
+        let err = provider
+            .install(InstallRequest {
+                name: "tf".to_string(),
+                version: Some("1.9.0".to_string()),
+                global: true,
+                force: false,
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CogniaError::PermissionDenied(_)));
+        assert!(!dir.path().join("versions/tf/1.9.0").exists());
+    }
+
+    #[tokio::test]
+    async fn test_install_and_uninstall_reject_escaping_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = make_provider(dir.path());
+        let outside = dir.path().join("outside");
+        std::fs::create_dir_all(&outside).unwrap();
+        let absolute = outside.display().to_string();
+
+        for version in ["../x", "/tmp/x", absolute.as_str(), "../../outside"] {
+            let err = provider
+                .install(InstallRequest {
+                    name: "tf".to_string(),
+                    version: Some(version.to_string()),
+                    global: true,
+                    force: true,
+                })
+                .await
+                .unwrap_err();
+            assert!(matches!(err, CogniaError::Provider(_)), "{}", version);
+
+            let err = provider
+                .uninstall(UninstallRequest {
+                    name: "tf".to_string(),
+                    version: Some(version.to_string()),
+                    force: true,
+                })
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("Invalid version"), "{}", version);
+        }
+        assert!(outside.is_dir());
+        assert!(provider.set_global_version("../x").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_detect_version_from_version_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = make_provider(dir.path());
+        let project = dir.path().join("project");
+        let nested = project.join("modules");
+        std::fs::create_dir_all(&nested).unwrap();
+        provider.set_local_version(&project, "1.8.5").await.unwrap();
+
+        let detected = provider.detect_version(&nested).await.unwrap().unwrap();
+        assert_eq!(detected.version, "1.8.5");
+        assert_eq!(detected.source_path, Some(project.join(".tf-version")));
+    }
+}
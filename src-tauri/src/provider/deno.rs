@@ -24,7 +24,7 @@ impl DenoProvider {
     pub fn new() -> Self {
         Self {
             deno_dir: Self::detect_deno_dir(),
-            client: crate::platform::proxy::get_shared_client(),
+            client: crate::platform::proxy::get_client_for_provider("deno"),
         }
     }
 
@@ -117,7 +117,7 @@ async fn get_deno_version(&self) -> CogniaResult<String> {
         Err(CogniaError::Provider("Could not parse Deno version".into()))
     }
 
-    async fn fetch_available_versions(&self) -> CogniaResult<Vec<String>> {
+    async fn fetch_available_versions(&self) -> CogniaResult<Vec<VersionInfo>> {
         // Fetch from GitHub releases API
         let url = "https://api.github.com/repos/denoland/deno/releases?per_page=50";
 
@@ -140,16 +140,89 @@ async fn fetch_available_versions(&self) -> CogniaResult<Vec<String>> {
             .await
             .map_err(|e| CogniaError::Parse(e.to_string()))?;
 
-        let versions: Vec<String> = releases
+        Ok(Self::release_versions(&releases))
+    }
+
+    /// Versions named by GitHub release tags (`v1.40.0` -> `1.40.0`),
+    /// dated by when the release was published.
+    fn release_versions(releases: &[serde_json::Value]) -> Vec<VersionInfo> {
+        releases
             .iter()
             .filter_map(|release| {
                 let tag = release["tag_name"].as_str()?;
                 // Tags are like "v1.40.0"
-                Some(tag.strip_prefix('v').unwrap_or(tag).to_string())
+                Some(VersionInfo {
+                    version: tag.strip_prefix('v').unwrap_or(tag).to_string(),
+                    release_date: release["published_at"].as_str().map(String::from),
+                    deprecated: false,
+                    yanked: false,
+                })
             })
-            .collect();
+            .collect()
+    }
+
+    /// Walk release pages newest-first until reaching a version in `cursor`,
+    /// returning only the versions published since. The first page is a
+    /// conditional request, so `None` means nothing changed.
+    async fn fetch_new_versions(
+        &self,
+        cursor: &VersionSyncCursor,
+    ) -> CogniaResult<Option<(Vec<VersionInfo>, Option<String>)>> {
+        const PER_PAGE: usize = 50;
+        const MAX_PAGES: usize = 10;
+
+        let mut new_versions = Vec::new();
+        let mut first_etag = None;
+        for page in 1..=MAX_PAGES {
+            let url = format!(
+                "https://api.github.com/repos/denoland/deno/releases?per_page={}&page={}",
+                PER_PAGE, page
+            );
+            let mut request = self.client.get(&url);
+            if page == 1 {
+                if let Some(etag) = &cursor.etag {
+                    request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+                }
+            }
+            let response = request
+                .send()
+                .await
+                .map_err(|e| CogniaError::Network(e.to_string()))?;
 
-        Ok(versions)
+            if page == 1 && response.status() == reqwest::StatusCode::NOT_MODIFIED {
+                return Ok(None);
+            }
+            if !response.status().is_success() {
+                return Err(CogniaError::Network(format!(
+                    "Failed to fetch Deno versions: {}",
+                    response.status()
+                )));
+            }
+            if page == 1 {
+                first_etag = response
+                    .headers()
+                    .get(reqwest::header::ETAG)
+                    .and_then(|value| value.to_str().ok())
+                    .map(String::from);
+            }
+
+            let releases: Vec<serde_json::Value> = response
+                .json()
+                .await
+                .map_err(|e| CogniaError::Parse(e.to_string()))?;
+            let page_len = releases.len();
+            for version in Self::release_versions(&releases) {
+                if cursor.known_versions.contains(&version.version) {
+                    return Ok(Some((new_versions, first_etag)));
+                }
+                new_versions.push(version);
+            }
+            if page_len < PER_PAGE {
+                break;
+            }
+        }
+
+        Ok(Some((new_versions, first_etag)))
     }
 }
 
@@ -177,6 +250,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::List,
             Capability::VersionSwitch,
             Capability::MultiVersion,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
 
@@ -208,12 +283,12 @@ async fn search(
 
         let results: Vec<PackageSummary> = versions
             .iter()
-            .filter(|v| v.contains(query) || query.is_empty())
+            .filter(|v| v.version.contains(query) || query.is_empty())
             .take(20)
-            .map(|version| PackageSummary {
-                name: format!("deno@{}", version),
+            .map(|v| PackageSummary {
+                name: format!("deno@{}", v.version),
                 description: Some("Deno - A secure runtime for JavaScript and TypeScript".into()),
-                latest_version: Some(version.clone()),
+                latest_version: Some(v.version.clone()),
                 provider: self.id().to_string(),
             })
             .collect();
@@ -242,17 +317,30 @@ async fn get_package_info(&self, name: &str) -> CogniaResult<PackageInfo> {
     }
 
     async fn get_versions(&self, _name: &str) -> CogniaResult<Vec<VersionInfo>> {
-        let versions = self.fetch_available_versions().await?;
+        self.fetch_available_versions().await
+    }
 
-        Ok(versions
-            .into_iter()
-            .map(|version| VersionInfo {
-                version,
-                release_date: None,
-                deprecated: false,
-                yanked: false,
-            })
-            .collect())
+    async fn fetch_versions_incremental(
+        &self,
+        name: &str,
+        cursor: &VersionSyncCursor,
+    ) -> CogniaResult<VersionFetch> {
+        if cursor.known_versions.is_empty() {
+            return Ok(VersionFetch::Full {
+                versions: self.get_versions(name).await?,
+                etag: None,
+                last_modified: None,
+            });
+        }
+
+        Ok(match self.fetch_new_versions(cursor).await? {
+            None => VersionFetch::Unchanged,
+            Some((versions, etag)) => VersionFetch::Delta {
+                versions,
+                etag,
+                last_modified: None,
+            },
+        })
     }
 
     async fn install(&self, req: InstallRequest) -> CogniaResult<InstallReceipt> {
@@ -348,6 +436,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                     install_path: dvm_dir.join("versions").join(version),
                     installed_at: String::new(),
                     is_global: true,
+                    kind: None,
                 });
             }
         } else {
@@ -368,6 +457,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                         install_path: deno_dir.join("bin"),
                         installed_at: String::new(),
                         is_global: true,
+                        kind: None,
                     });
                 }
             }
@@ -385,11 +475,11 @@ async fn check_updates(&self, _packages: &[String]) -> CogniaResult<Vec<UpdateIn
 
         let versions = self.fetch_available_versions().await.unwrap_or_default();
         if let Some(latest) = versions.first() {
-            if *latest != current {
+            if latest.version != current {
                 return Ok(vec![UpdateInfo {
                     name: "deno".into(),
                     current_version: current,
-                    latest_version: latest.clone(),
+                    latest_version: latest.version.clone(),
                     provider: self.id().into(),
                 }]);
             }
@@ -627,4 +717,22 @@ fn test_version_file_name() {
     fn test_default_impl() {
         let _provider = DenoProvider::default();
     }
+
+    #[test]
+    fn test_release_versions_carry_publish_dates() {
+        let releases = vec![
+            serde_json::json!({"tag_name": "v2.1.0", "published_at": "2024-11-21T12:00:00Z"}),
+            serde_json::json!({"tag_name": "v2.0.6"}),
+            serde_json::json!({"name": "untagged"}),
+        ];
+        let versions = DenoProvider::release_versions(&releases);
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].version, "2.1.0");
+        assert_eq!(
+            versions[0].release_date.as_deref(),
+            Some("2024-11-21T12:00:00Z")
+        );
+        assert_eq!(versions[1].version, "2.0.6");
+        assert!(versions[1].release_date.is_none());
+    }
 }
@@ -94,6 +94,9 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::Update,
             Capability::Upgrade,
             Capability::UpdateIndex,
+            Capability::Info,
+            Capability::Versions,
+            Capability::RequiresElevation,
         ])
     }
 
@@ -296,6 +299,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                         install_path: PathBuf::from("/usr"),
                         installed_at: String::new(),
                         is_global: true,
+                        kind: None,
                     })
                 } else {
                     None
@@ -463,7 +467,7 @@ fn test_capabilities() {
         assert!(caps.contains(&Capability::Update));
         assert!(caps.contains(&Capability::Upgrade));
         assert!(caps.contains(&Capability::UpdateIndex));
-        assert_eq!(caps.len(), 7);
+        assert_eq!(caps.len(), 10);
     }
 
     #[test]
@@ -578,6 +582,7 @@ fn test_parse_dnf_list_installed_output() {
                         install_path: PathBuf::from("/usr"),
                         installed_at: String::new(),
                         is_global: true,
+                        kind: None,
                     })
                 } else {
                     None
@@ -178,14 +178,20 @@ fn display_name(&self) -> &str {
         "Docker (Container Images)"
     }
     fn capabilities(&self) -> HashSet<Capability> {
-        HashSet::from([
+        let mut capabilities = HashSet::from([
             Capability::Install,
             Capability::Uninstall,
             Capability::Search,
             Capability::List,
             Capability::Update,
             Capability::Upgrade,
-        ])
+            Capability::Info,
+            Capability::Versions,
+        ]);
+        if cfg!(target_os = "linux") {
+            capabilities.insert(Capability::RequiresElevation);
+        }
+        capabilities
     }
     fn supported_platforms(&self) -> Vec<Platform> {
         vec![Platform::Windows, Platform::MacOS, Platform::Linux]
@@ -506,6 +512,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                     install_path: PathBuf::new(),
                     installed_at: created,
                     is_global: true,
+                    kind: None,
                 })
             })
             .collect();
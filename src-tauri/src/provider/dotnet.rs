@@ -13,10 +13,16 @@
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
+const DOTNET_INSTALL_SCRIPT_BASE: &str = "https://dot.net/v1/dotnet-install";
+const RELEASES_INDEX_URL: &str =
+    "https://builds.dotnet.microsoft.com/dotnet/release-metadata/releases-index.json";
+
 /// .NET SDK Provider - Environment and package management for .NET
 ///
 /// Provides .NET SDK version management and NuGet package search capabilities.
-/// Supports global.json for project-local version pinning.
+/// SDKs and runtimes are installed with the official dotnet-install scripts
+/// into a Cognia-managed DOTNET_ROOT. Supports global.json for project-local
+/// version pinning.
 pub struct DotnetProvider {
     client: Client,
 }
@@ -24,13 +30,41 @@ pub struct DotnetProvider {
 impl DotnetProvider {
     pub fn new() -> Self {
         Self {
-            client: crate::platform::proxy::get_shared_client(),
+            client: crate::platform::proxy::get_client_for_provider("dotnet"),
         }
     }
 
+    /// DOTNET_ROOT that dotnet-install populates
+    fn managed_root() -> Option<PathBuf> {
+        fs::get_environments_dir().map(|dir| dir.join("dotnet"))
+    }
+
+    /// The managed `dotnet` executable, once something has been installed
+    fn managed_dotnet() -> Option<PathBuf> {
+        let exe = if cfg!(windows) {
+            "dotnet.exe"
+        } else {
+            "dotnet"
+        };
+        Self::managed_root()
+            .map(|root| root.join(exe))
+            .filter(|path| path.is_file())
+    }
+
+    /// Prefer the managed install so listings match what Cognia put on PATH
+    fn dotnet_command() -> String {
+        Self::managed_dotnet()
+            .map(|path| path.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "dotnet".to_string())
+    }
+
     async fn run_dotnet(&self, args: &[&str]) -> CogniaResult<String> {
+        Self::run_dotnet_with(&Self::dotnet_command(), args).await
+    }
+
+    async fn run_dotnet_with(program: &str, args: &[&str]) -> CogniaResult<String> {
         let opts = ProcessOptions::new().with_timeout(Duration::from_secs(120));
-        let output = process::execute("dotnet", args, Some(opts)).await?;
+        let output = process::execute(program, args, Some(opts)).await?;
         if output.success {
             Ok(output.stdout)
         } else {
@@ -38,6 +72,210 @@ async fn run_dotnet(&self, args: &[&str]) -> CogniaResult<String> {
         }
     }
 
+    async fn fetch_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> CogniaResult<T> {
+        let response = self
+            .client
+            .get(url)
+            .timeout(Duration::from_secs(30))
+            .send()
+            .await
+            .map_err(|e| CogniaError::Network(format!("Request to {} failed: {}", url, e)))?;
+        if !response.status().is_success() {
+            return Err(CogniaError::Network(format!(
+                "{} returned status: {}",
+                url,
+                response.status()
+            )));
+        }
+        response
+            .json()
+            .await
+            .map_err(|e| CogniaError::Provider(format!("Failed to parse {}: {}", url, e)))
+    }
+
+    /// Available versions of an SDK or runtime from the release metadata feed.
+    /// End-of-life channels only contribute their final release.
+    async fn fetch_component_versions(
+        &self,
+        component: DotnetComponent,
+    ) -> CogniaResult<Vec<VersionInfo>> {
+        let index: ReleasesIndex = self.fetch_json(RELEASES_INDEX_URL).await?;
+        let mut seen = HashSet::new();
+        let mut versions = Vec::new();
+
+        for channel in index.releases_index {
+            let eol = channel.support_phase.eq_ignore_ascii_case("eol");
+            let latest = match component {
+                DotnetComponent::Sdk => channel.latest_sdk.clone(),
+                DotnetComponent::Runtime(_) => channel.latest_runtime.clone(),
+            };
+
+            let releases = if eol {
+                None
+            } else {
+                match self
+                    .fetch_json::<ChannelReleases>(&channel.releases_json)
+                    .await
+                {
+                    Ok(releases) => Some(releases.releases),
+                    Err(e) => {
+                        tracing::debug!(
+                            "Failed to fetch .NET {} releases: {}",
+                            channel.channel_version,
+                            e
+                        );
+                        None
+                    }
+                }
+            };
+
+            let Some(releases) = releases else {
+                if seen.insert(latest.clone()) {
+                    versions.push(VersionInfo {
+                        version: latest,
+                        release_date: channel.latest_release_date,
+                        deprecated: eol,
+                        yanked: false,
+                    });
+                }
+                continue;
+            };
+
+            for release in releases {
+                for version in release.component_versions(component) {
+                    if seen.insert(version.clone()) {
+                        versions.push(VersionInfo {
+                            version,
+                            release_date: release.release_date.clone(),
+                            deprecated: false,
+                            yanked: false,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(versions)
+    }
+
+    /// Versions of `component` present under the managed root
+    async fn managed_versions(component: DotnetComponent, root: &Path) -> Vec<String> {
+        let Ok(mut entries) = tokio::fs::read_dir(component.versions_dir(root)).await else {
+            return Vec::new();
+        };
+        let mut versions = Vec::new();
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            if entry.path().is_dir() {
+                versions.push(entry.file_name().to_string_lossy().into_owned());
+            }
+        }
+        versions
+    }
+
+    async fn fetch_install_script(&self, root: &Path) -> CogniaResult<PathBuf> {
+        let extension = if cfg!(windows) { "ps1" } else { "sh" };
+        let url = format!("{}.{}", DOTNET_INSTALL_SCRIPT_BASE, extension);
+        let response = self
+            .client
+            .get(&url)
+            .timeout(Duration::from_secs(60))
+            .send()
+            .await
+            .map_err(|e| CogniaError::Network(format!("Failed to download {}: {}", url, e)))?;
+        if !response.status().is_success() {
+            return Err(CogniaError::Network(format!(
+                "{} returned status: {}",
+                url,
+                response.status()
+            )));
+        }
+        let script = response
+            .bytes()
+            .await
+            .map_err(|e| CogniaError::Network(format!("Failed to download {}: {}", url, e)))?;
+
+        let path = root.join(format!("dotnet-install.{}", extension));
+        fs::write_file(&path, &script).await?;
+        Ok(path)
+    }
+
+    /// Install an SDK or runtime into the managed root with dotnet-install
+    async fn install_component(
+        &self,
+        component: DotnetComponent,
+        req: &InstallRequest,
+    ) -> CogniaResult<InstallReceipt> {
+        let root = Self::managed_root().ok_or_else(|| {
+            CogniaError::Provider("Cannot determine Cognia data directory".into())
+        })?;
+        fs::create_dir_all(&root).await?;
+
+        let requested = req.version.as_deref().unwrap_or("latest");
+        let before = Self::managed_versions(component, &root).await;
+        let script = self.fetch_install_script(&root).await?;
+
+        let (program, mut args) = if cfg!(windows) {
+            (
+                "powershell",
+                vec![
+                    "-NoProfile".to_string(),
+                    "-ExecutionPolicy".to_string(),
+                    "Bypass".to_string(),
+                    "-File".to_string(),
+                    script.to_string_lossy().into_owned(),
+                ],
+            )
+        } else {
+            ("bash", vec![script.to_string_lossy().into_owned()])
+        };
+        args.extend(install_script_args(
+            component,
+            requested,
+            &root,
+            cfg!(windows),
+        ));
+        let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+
+        let opts = ProcessOptions::new().with_timeout(Duration::from_secs(1800));
+        let output = process::execute(program, &arg_refs, Some(opts)).await?;
+        if !output.success {
+            let detail = if output.stderr.trim().is_empty() {
+                output.stdout.trim()
+            } else {
+                output.stderr.trim()
+            };
+            return Err(CogniaError::Installation(format!(
+                "dotnet-install failed for {} {}: {}",
+                req.name, requested, detail
+            )));
+        }
+
+        // Channel requests ("8.0", "lts") only reveal the concrete version
+        // once the files are on disk.
+        let after = Self::managed_versions(component, &root).await;
+        let version = after
+            .iter()
+            .find(|v| !before.contains(v))
+            .cloned()
+            .or_else(|| after.iter().find(|v| *v == requested).cloned())
+            .unwrap_or_else(|| requested.to_string());
+
+        Ok(InstallReceipt {
+            name: req.name.clone(),
+            install_path: component.versions_dir(&root).join(&version),
+            version,
+            provider: self.id().into(),
+            files: vec![],
+            installed_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// Runtimes visible to the preferred `dotnet`, from `dotnet --list-runtimes`
+    pub async fn list_runtimes(&self) -> CogniaResult<Vec<DotnetRuntime>> {
+        let output = self.run_dotnet(&["--list-runtimes"]).await?;
+        Ok(Self::parse_runtime_list(&output))
+    }
+
     /// Get NuGet packages directory
     fn get_nuget_packages_dir() -> Option<PathBuf> {
         // Check NUGET_PACKAGES env var first
@@ -132,7 +370,11 @@ async fn get_nuget_package(&self, id: &str) -> CogniaResult<NuGetPackageInfo> {
     }
 
     fn get_dotnet_root() -> Option<PathBuf> {
-        // Check DOTNET_ROOT first
+        if let Some(exe) = Self::managed_dotnet() {
+            return exe.parent().map(Path::to_path_buf);
+        }
+
+        // Then DOTNET_ROOT
         if let Ok(root) = std::env::var("DOTNET_ROOT") {
             return Some(PathBuf::from(root));
         }
@@ -178,6 +420,29 @@ fn parse_sdk_list(output: &str) -> Vec<InstalledVersion> {
         versions
     }
 
+    fn parse_runtime_list(output: &str) -> Vec<DotnetRuntime> {
+        output
+            .lines()
+            .filter_map(|line| {
+                // Format: "Microsoft.NETCore.App 8.0.0 [path]"
+                let (name, rest) = line.trim().split_once(' ')?;
+                let (version, path_part) = rest.split_once(' ')?;
+                let path = path_part
+                    .trim()
+                    .trim_start_matches('[')
+                    .trim_end_matches(']');
+                Some(DotnetRuntime {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    install_path: PathBuf::from(path)
+                        .join(version)
+                        .to_string_lossy()
+                        .into_owned(),
+                })
+            })
+            .collect()
+    }
+
     async fn read_global_json(path: &Path) -> Option<String> {
         let global_json_path = path.join("global.json");
         if let Ok(content) = fs::read_file_string(&global_json_path).await {
@@ -188,18 +453,16 @@ async fn read_global_json(path: &Path) -> Option<String> {
         None
     }
 
+    /// Pin `sdk.version`, keeping every other setting already in the file
     async fn write_global_json(path: &Path, version: &str) -> CogniaResult<()> {
         let global_json_path = path.join("global.json");
-        let content = GlobalJson {
-            sdk: Some(SdkConfig {
-                version: Some(version.to_string()),
-                roll_forward: Some("latestFeature".to_string()),
-            }),
+        let existing = match fs::read_file_string(&global_json_path).await {
+            Ok(content) => Some(content),
+            Err(_) if !global_json_path.exists() => None,
+            Err(e) => return Err(e.into()),
         };
 
-        let json = serde_json::to_string_pretty(&content).map_err(|e| {
-            CogniaError::Provider(format!("Failed to serialize global.json: {}", e))
-        })?;
+        let json = update_global_json(existing.as_deref(), version)?;
 
         fs::write_file(&global_json_path, json.as_bytes()).await?;
         Ok(())
@@ -302,6 +565,16 @@ pub struct NuGetPackageInfo {
     pub versions: Vec<NuGetPackageVersion>,
 }
 
+/// Runtime entry from `dotnet --list-runtimes`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DotnetRuntime {
+    /// Shared framework name, e.g. `Microsoft.NETCore.App`
+    pub name: String,
+    pub version: String,
+    pub install_path: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct GlobalJson {
     #[serde(default)]
@@ -315,6 +588,314 @@ struct SdkConfig {
     #[serde(rename = "rollForward")]
     #[serde(default)]
     roll_forward: Option<String>,
+    #[serde(rename = "allowPrerelease")]
+    #[serde(default)]
+    allow_prerelease: Option<bool>,
+}
+
+// Release metadata feed types
+#[derive(Debug, Deserialize)]
+struct ReleasesIndex {
+    #[serde(rename = "releases-index")]
+    releases_index: Vec<ReleaseChannel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseChannel {
+    #[serde(rename = "channel-version")]
+    channel_version: String,
+    #[serde(rename = "latest-release-date")]
+    #[serde(default)]
+    latest_release_date: Option<String>,
+    #[serde(rename = "latest-runtime")]
+    latest_runtime: String,
+    #[serde(rename = "latest-sdk")]
+    latest_sdk: String,
+    #[serde(rename = "support-phase")]
+    support_phase: String,
+    #[serde(rename = "releases.json")]
+    releases_json: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChannelReleases {
+    releases: Vec<ChannelRelease>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChannelRelease {
+    #[serde(rename = "release-date")]
+    #[serde(default)]
+    release_date: Option<String>,
+    #[serde(default)]
+    sdks: Vec<ReleaseComponent>,
+    #[serde(default)]
+    runtime: Option<ReleaseComponent>,
+    #[serde(rename = "aspnetcore-runtime")]
+    #[serde(default)]
+    aspnetcore_runtime: Option<ReleaseComponent>,
+    #[serde(rename = "windowsdesktop")]
+    #[serde(default)]
+    windowsdesktop: Option<ReleaseComponent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseComponent {
+    version: String,
+}
+
+impl ChannelRelease {
+    fn component_versions(&self, component: DotnetComponent) -> Vec<String> {
+        match component {
+            DotnetComponent::Sdk => self.sdks.iter().map(|s| s.version.clone()).collect(),
+            DotnetComponent::Runtime(runtime) => match runtime {
+                "aspnetcore" => self.aspnetcore_runtime.as_ref(),
+                "windowsdesktop" => self.windowsdesktop.as_ref(),
+                _ => self.runtime.as_ref(),
+            }
+            .map(|r| vec![r.version.clone()])
+            .unwrap_or_default(),
+        }
+    }
+}
+
+/// What dotnet-install should fetch for a package name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DotnetComponent {
+    Sdk,
+    /// `--runtime` value: `dotnet`, `aspnetcore` or `windowsdesktop`
+    Runtime(&'static str),
+}
+
+impl DotnetComponent {
+    /// Names other than these are NuGet packages
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "dotnet" | "dotnet-sdk" => Some(Self::Sdk),
+            "dotnet-runtime" => Some(Self::Runtime("dotnet")),
+            "aspnetcore-runtime" => Some(Self::Runtime("aspnetcore")),
+            "windowsdesktop-runtime" => Some(Self::Runtime("windowsdesktop")),
+            _ => None,
+        }
+    }
+
+    /// Directory under DOTNET_ROOT holding one subdirectory per version
+    fn versions_dir(self, root: &Path) -> PathBuf {
+        match self {
+            Self::Sdk => root.join("sdk"),
+            Self::Runtime("aspnetcore") => root.join("shared").join("Microsoft.AspNetCore.App"),
+            Self::Runtime("windowsdesktop") => {
+                root.join("shared").join("Microsoft.WindowsDesktop.App")
+            }
+            Self::Runtime(_) => root.join("shared").join("Microsoft.NETCore.App"),
+        }
+    }
+}
+
+/// dotnet-install arguments. Two-part versions and `lts`/`sts`/`latest` are
+/// channels; anything else is an exact version.
+fn install_script_args(
+    component: DotnetComponent,
+    version: &str,
+    install_dir: &Path,
+    powershell: bool,
+) -> Vec<String> {
+    let flag = |bash: &str, ps: &str| if powershell { ps } else { bash }.to_string();
+
+    let is_channel = matches!(version.to_ascii_lowercase().as_str(), "lts" | "sts")
+        || (version.split('.').count() == 2
+            && version.split('.').all(|p| p.parse::<u32>().is_ok()));
+
+    let mut args = Vec::new();
+    if version.eq_ignore_ascii_case("latest") {
+        args.extend([flag("--version", "-Version"), "latest".to_string()]);
+    } else if is_channel {
+        args.extend([flag("--channel", "-Channel"), version.to_uppercase()]);
+    } else {
+        args.extend([flag("--version", "-Version"), version.to_string()]);
+    }
+    if let DotnetComponent::Runtime(runtime) = component {
+        args.extend([flag("--runtime", "-Runtime"), runtime.to_string()]);
+    }
+    args.extend([
+        flag("--install-dir", "-InstallDir"),
+        install_dir.to_string_lossy().into_owned(),
+        flag("--no-path", "-NoPath"),
+    ]);
+    args
+}
+
+/// Set `sdk.version` in global.json content, keeping `rollForward` and any
+/// other fields. New pins default to `rollForward: latestFeature`.
+fn update_global_json(existing: Option<&str>, version: &str) -> CogniaResult<String> {
+    let mut root = match existing.map(str::trim).filter(|c| !c.is_empty()) {
+        Some(content) => serde_json::from_str::<serde_json::Value>(content)
+            .map_err(|e| CogniaError::Parse(format!("Invalid global.json: {}", e)))?,
+        None => serde_json::json!({}),
+    };
+    let obj = root
+        .as_object_mut()
+        .ok_or_else(|| CogniaError::Parse("global.json must be a JSON object".into()))?;
+
+    let sdk = obj.entry("sdk").or_insert_with(|| serde_json::json!({}));
+    if !sdk.is_object() {
+        *sdk = serde_json::json!({});
+    }
+    if let Some(sdk) = sdk.as_object_mut() {
+        sdk.insert("version".into(), serde_json::Value::String(version.into()));
+        sdk.entry("rollForward")
+            .or_insert_with(|| serde_json::Value::String("latestFeature".into()));
+    }
+
+    serde_json::to_string_pretty(&root)
+        .map_err(|e| CogniaError::Provider(format!("Failed to serialize global.json: {}", e)))
+}
+
+/// An SDK version split the way rollForward sees it: `8.0.203` is feature
+/// band 2, patch 3.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct SdkVersion {
+    major: u32,
+    minor: u32,
+    feature: u32,
+    patch: u32,
+    /// Releases sort after any prerelease of the same number
+    release: bool,
+    prerelease: String,
+    raw: String,
+}
+
+impl SdkVersion {
+    fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim();
+        let (core, prerelease) = match raw.split_once('-') {
+            Some((core, pre)) => (core, pre),
+            None => (raw, ""),
+        };
+        let mut parts = core.split('.').map(|p| p.parse::<u32>().ok());
+        let major = parts.next()??;
+        let minor = parts.next()??;
+        let patch = parts.next()??;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self {
+            major,
+            minor,
+            feature: patch / 100,
+            patch: patch % 100,
+            release: prerelease.is_empty(),
+            prerelease: prerelease.to_string(),
+            raw: raw.to_string(),
+        })
+    }
+}
+
+/// How far a rollForward policy may move away from the requested SDK
+#[derive(Debug, Clone, Copy)]
+enum RollScope {
+    FeatureBand,
+    Minor,
+    Major,
+    Any,
+}
+
+impl RollScope {
+    fn contains(self, requested: &SdkVersion, v: &SdkVersion) -> bool {
+        let same_major = v.major == requested.major;
+        let same_minor = same_major && v.minor == requested.minor;
+        match self {
+            Self::FeatureBand => same_minor && v.feature == requested.feature,
+            Self::Minor => same_minor,
+            Self::Major => same_major,
+            Self::Any => true,
+        }
+    }
+}
+
+/// Pick the installed SDK the dotnet host would use for a global.json pin,
+/// following the documented rollForward policies. `installed` may be in any
+/// order; `None` means no installed SDK satisfies the pin.
+pub fn resolve_sdk(
+    requested: Option<&str>,
+    roll_forward: Option<&str>,
+    allow_prerelease: bool,
+    installed: &[String],
+) -> Option<String> {
+    let requested = requested.and_then(SdkVersion::parse);
+    // A prerelease pin always considers prereleases
+    let allow_prerelease = allow_prerelease || requested.as_ref().is_some_and(|r| !r.release);
+    let mut candidates: Vec<SdkVersion> = installed
+        .iter()
+        .filter_map(|v| SdkVersion::parse(v))
+        .filter(|v| allow_prerelease || v.release)
+        .collect();
+    candidates.sort();
+
+    let Some(requested) = requested else {
+        // Without a version the host uses the newest SDK
+        return candidates.pop().map(|v| v.raw);
+    };
+    let at_least: Vec<&SdkVersion> = candidates.iter().filter(|v| **v >= requested).collect();
+
+    let exact = || at_least.iter().find(|v| v.raw == requested.raw).copied();
+    // The lowest band in scope, at its highest patch. Only `patch` and
+    // `disable` prefer the exact version; the wider policies take the
+    // latest patch even when the requested one is installed.
+    let nearest = |scope: RollScope| {
+        let first = at_least.iter().find(|v| scope.contains(&requested, v))?;
+        at_least
+            .iter()
+            .rev()
+            .find(|v| RollScope::FeatureBand.contains(first, v))
+            .copied()
+    };
+    let latest = |scope: RollScope| {
+        at_least
+            .iter()
+            .rev()
+            .find(|v| scope.contains(&requested, v))
+            .copied()
+    };
+
+    let resolved = match roll_forward
+        .unwrap_or("patch")
+        .to_ascii_lowercase()
+        .as_str()
+    {
+        "disable" => exact(),
+        "patch" => exact().or_else(|| nearest(RollScope::FeatureBand)),
+        "feature" => nearest(RollScope::Minor),
+        "minor" => nearest(RollScope::Major),
+        "major" => nearest(RollScope::Any),
+        "latestpatch" => latest(RollScope::FeatureBand),
+        "latestfeature" => latest(RollScope::Minor),
+        "latestminor" => latest(RollScope::Major),
+        "latestmajor" => latest(RollScope::Any),
+        _ => None,
+    };
+    resolved.map(|v| v.raw.clone())
+}
+
+/// The SDK a global.json actually selects among the SDKs visible to the
+/// preferred `dotnet`
+pub async fn resolve_global_json_sdk(global_json: &Path) -> Option<String> {
+    let content = fs::read_file_string(global_json).await.ok()?;
+    let sdk = serde_json::from_str::<GlobalJson>(&content).ok()?.sdk?;
+    let output =
+        DotnetProvider::run_dotnet_with(&DotnetProvider::dotnet_command(), &["--list-sdks"])
+            .await
+            .ok()?;
+    let installed: Vec<String> = DotnetProvider::parse_sdk_list(&output)
+        .into_iter()
+        .map(|v| v.version)
+        .collect();
+    resolve_sdk(
+        sdk.version.as_deref(),
+        sdk.roll_forward.as_deref(),
+        sdk.allow_prerelease.unwrap_or(true),
+        &installed,
+    )
 }
 
 #[async_trait]
@@ -336,6 +917,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::VersionSwitch,
             Capability::MultiVersion,
             Capability::ProjectLocal,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
 
@@ -348,7 +931,8 @@ fn priority(&self) -> i32 {
     }
 
     async fn is_available(&self) -> bool {
-        system_detection::is_command_available("dotnet", &["--version"]).await
+        Self::managed_dotnet().is_some()
+            || system_detection::is_command_available("dotnet", &["--version"]).await
     }
 
     async fn search(
@@ -407,6 +991,10 @@ async fn get_package_info(&self, name: &str) -> CogniaResult<PackageInfo> {
     }
 
     async fn get_versions(&self, name: &str) -> CogniaResult<Vec<VersionInfo>> {
+        if let Some(component) = DotnetComponent::from_name(name) {
+            return self.fetch_component_versions(component).await;
+        }
+
         let info = self.get_nuget_package(name).await?;
 
         Ok(info
@@ -429,7 +1017,7 @@ async fn get_dependencies(&self, name: &str, version: &str) -> CogniaResult<Vec<
             name.to_lowercase()
         );
 
-        let client = crate::platform::proxy::get_shared_client();
+        let client = crate::platform::proxy::get_client_for_provider("dotnet");
 
         if let Ok(resp) = client
             .get(&url)
@@ -493,6 +1081,10 @@ async fn get_dependencies(&self, name: &str, version: &str) -> CogniaResult<Vec<
     }
 
     async fn install(&self, req: InstallRequest) -> CogniaResult<InstallReceipt> {
+        if let Some(component) = DotnetComponent::from_name(&req.name) {
+            return self.install_component(component, &req).await;
+        }
+
         // For NuGet packages, use dotnet add package
         let mut args = vec!["add", "package", &req.name];
         let version_arg;
@@ -525,6 +1117,24 @@ async fn install(&self, req: InstallRequest) -> CogniaResult<InstallReceipt> {
     }
 
     async fn uninstall(&self, req: UninstallRequest) -> CogniaResult<()> {
+        if let Some(component) = DotnetComponent::from_name(&req.name) {
+            let version = req.version.as_deref().ok_or_else(|| {
+                CogniaError::Provider(format!("A version is required to uninstall {}", req.name))
+            })?;
+            crate::core::custom_env_type::validate_version(version)?;
+            let dir = Self::managed_root()
+                .map(|root| component.versions_dir(&root).join(version))
+                .filter(|dir| dir.is_dir())
+                .ok_or_else(|| {
+                    CogniaError::Provider(format!(
+                        "{} {} was not installed by Cognia; remove it with the installer it came from",
+                        req.name, version
+                    ))
+                })?;
+            fs::remove_dir_all(&dir).await?;
+            return Ok(());
+        }
+
         self.run_dotnet(&["remove", "package", &req.name]).await?;
         Ok(())
     }
@@ -568,6 +1178,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                         install_path: nuget_path,
                         installed_at: String::new(),
                         is_global: false,
+                        kind: None,
                     });
                 }
             }
@@ -617,8 +1228,26 @@ async fn check_updates(&self, packages: &[String]) -> CogniaResult<Vec<UpdateInf
 #[async_trait]
 impl EnvironmentProvider for DotnetProvider {
     async fn list_installed_versions(&self) -> CogniaResult<Vec<InstalledVersion>> {
-        let output = self.run_dotnet(&["--list-sdks"]).await?;
-        let mut versions = Self::parse_sdk_list(&output);
+        // Managed SDKs first, then any system SDKs it doesn't already have
+        let managed = match Self::managed_dotnet() {
+            Some(exe) => Self::run_dotnet_with(&exe.to_string_lossy(), &["--list-sdks"])
+                .await
+                .ok(),
+            None => None,
+        };
+        let system = Self::run_dotnet_with("dotnet", &["--list-sdks"]).await;
+        let mut versions = match (managed, system) {
+            (None, Err(e)) => return Err(e),
+            (managed, system) => {
+                let mut versions = Self::parse_sdk_list(managed.as_deref().unwrap_or(""));
+                for v in Self::parse_sdk_list(system.as_deref().unwrap_or("")) {
+                    if !versions.iter().any(|m| m.version == v.version) {
+                        versions.push(v);
+                    }
+                }
+                versions
+            }
+        };
 
         // Mark current version
         if let Ok(Some(current)) = self.get_current_version().await {
@@ -679,8 +1308,10 @@ async fn detect_version(&self, start_path: &Path) -> CogniaResult<Option<Version
         Ok(None)
     }
 
-    fn get_env_modifications(&self, _version: &str) -> CogniaResult<EnvModifications> {
-        let dotnet_root = Self::get_dotnet_root()
+    fn get_env_modifications(&self, version: &str) -> CogniaResult<EnvModifications> {
+        let dotnet_root = Self::managed_root()
+            .filter(|root| root.join("sdk").join(version).is_dir())
+            .or_else(Self::get_dotnet_root)
             .ok_or_else(|| CogniaError::Provider("DOTNET_ROOT not found".into()))?;
 
         let mut set_variables = std::collections::HashMap::new();
@@ -722,6 +1353,9 @@ async fn get_version(&self) -> CogniaResult<String> {
     }
 
     async fn get_executable_path(&self) -> CogniaResult<PathBuf> {
+        if let Some(exe) = Self::managed_dotnet() {
+            return Ok(exe);
+        }
         process::which("dotnet")
             .await
             .map(PathBuf::from)
@@ -798,4 +1432,164 @@ fn test_priority() {
         let provider = DotnetProvider::new();
         assert_eq!(provider.priority(), 80);
     }
+
+    #[test]
+    fn test_parse_runtime_list() {
+        let output =
+            "Microsoft.AspNetCore.App 8.0.1 [/usr/share/dotnet/shared/Microsoft.AspNetCore.App]
+Microsoft.NETCore.App 8.0.1 [/usr/share/dotnet/shared/Microsoft.NETCore.App]
+";
+
+        let runtimes = DotnetProvider::parse_runtime_list(output);
+
+        assert_eq!(runtimes.len(), 2);
+        assert_eq!(runtimes[1].name, "Microsoft.NETCore.App");
+        assert_eq!(runtimes[1].version, "8.0.1");
+        assert!(runtimes[1].install_path.ends_with("8.0.1"));
+    }
+
+    #[test]
+    fn test_install_script_args() {
+        let dir = Path::new("/opt/dotnet");
+        let sdk = install_script_args(DotnetComponent::Sdk, "8.0.100", dir, false);
+        assert_eq!(
+            sdk,
+            vec![
+                "--version",
+                "8.0.100",
+                "--install-dir",
+                "/opt/dotnet",
+                "--no-path"
+            ]
+        );
+
+        let runtime = install_script_args(DotnetComponent::Runtime("aspnetcore"), "8.0", dir, true);
+        assert_eq!(
+            runtime,
+            vec![
+                "-Channel",
+                "8.0",
+                "-Runtime",
+                "aspnetcore",
+                "-InstallDir",
+                "/opt/dotnet",
+                "-NoPath"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_update_global_json_preserves_settings() {
+        let existing = r#"{"sdk": {"version": "6.0.100", "rollForward": "disable"}, "msbuild-sdks": {"Foo": "1.0.0"}}"#;
+        let updated: serde_json::Value =
+            serde_json::from_str(&update_global_json(Some(existing), "8.0.100").unwrap()).unwrap();
+
+        assert_eq!(updated["sdk"]["version"], "8.0.100");
+        assert_eq!(updated["sdk"]["rollForward"], "disable");
+        assert_eq!(updated["msbuild-sdks"]["Foo"], "1.0.0");
+
+        let created: serde_json::Value =
+            serde_json::from_str(&update_global_json(None, "8.0.100").unwrap()).unwrap();
+        assert_eq!(created["sdk"]["rollForward"], "latestFeature");
+
+        assert!(update_global_json(Some("{ not json"), "8.0.100").is_err());
+    }
+
+    fn installed(versions: &[&str]) -> Vec<String> {
+        versions.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_resolve_sdk_nearest_policies() {
+        let sdks = installed(&[
+            "6.0.100", "8.0.101", "8.0.103", "8.0.204", "8.0.301", "8.1.100", "9.0.100",
+        ]);
+        let resolve =
+            |version: &str, policy: &str| resolve_sdk(Some(version), Some(policy), true, &sdks);
+
+        assert_eq!(resolve("8.0.101", "disable").as_deref(), Some("8.0.101"));
+        assert_eq!(resolve("8.0.102", "disable"), None);
+        assert_eq!(resolve("8.0.101", "patch").as_deref(), Some("8.0.101"));
+        assert_eq!(resolve("8.0.102", "patch").as_deref(), Some("8.0.103"));
+        assert_eq!(resolve("8.0.104", "patch"), None);
+        // The wider policies take the latest patch of the requested band
+        // even when the exact version is installed
+        assert_eq!(resolve("8.0.101", "feature").as_deref(), Some("8.0.103"));
+        assert_eq!(resolve("8.0.101", "minor").as_deref(), Some("8.0.103"));
+        assert_eq!(resolve("8.0.101", "major").as_deref(), Some("8.0.103"));
+        assert_eq!(resolve("8.0.104", "feature").as_deref(), Some("8.0.204"));
+        assert_eq!(resolve("8.0.400", "feature"), None);
+        assert_eq!(resolve("8.0.400", "minor").as_deref(), Some("8.1.100"));
+        assert_eq!(resolve("8.2.100", "major").as_deref(), Some("9.0.100"));
+        // Defaults to patch when a version is pinned
+        assert_eq!(
+            resolve_sdk(Some("8.0.102"), None, true, &sdks).as_deref(),
+            Some("8.0.103")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_uninstall_rejects_path_versions() {
+        let provider = DotnetProvider::new();
+        for version in ["../8.0.100", "8.0.100/../../x", ".."] {
+            let err = provider
+                .uninstall(UninstallRequest {
+                    name: "dotnet-sdk".into(),
+                    version: Some(version.into()),
+                    force: false,
+                })
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("Invalid version"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_resolve_sdk_latest_policies() {
+        let sdks = installed(&["8.0.101", "8.0.103", "8.0.204", "8.1.100", "9.0.100"]);
+        let resolve =
+            |version: &str, policy: &str| resolve_sdk(Some(version), Some(policy), true, &sdks);
+
+        assert_eq!(
+            resolve("8.0.100", "latestPatch").as_deref(),
+            Some("8.0.103")
+        );
+        assert_eq!(
+            resolve("8.0.100", "latestFeature").as_deref(),
+            Some("8.0.204")
+        );
+        assert_eq!(
+            resolve("8.0.100", "latestMinor").as_deref(),
+            Some("8.1.100")
+        );
+        assert_eq!(
+            resolve("8.0.100", "latestMajor").as_deref(),
+            Some("9.0.100")
+        );
+        assert_eq!(resolve("9.0.200", "latestMajor"), None);
+        assert_eq!(resolve("8.0.100", "sideways"), None);
+    }
+
+    #[test]
+    fn test_resolve_sdk_prereleases() {
+        let sdks = installed(&["8.0.100", "9.0.100-rc.2.24474.11"]);
+
+        assert_eq!(
+            resolve_sdk(None, None, true, &sdks).as_deref(),
+            Some("9.0.100-rc.2.24474.11")
+        );
+        assert_eq!(
+            resolve_sdk(None, None, false, &sdks).as_deref(),
+            Some("8.0.100")
+        );
+        assert_eq!(
+            resolve_sdk(Some("8.0.100"), Some("latestMajor"), false, &sdks).as_deref(),
+            Some("8.0.100")
+        );
+        // A prerelease pin considers prereleases regardless of the setting
+        assert_eq!(
+            resolve_sdk(Some("9.0.100-rc.1.0"), Some("patch"), false, &sdks).as_deref(),
+            Some("9.0.100-rc.2.24474.11")
+        );
+    }
 }
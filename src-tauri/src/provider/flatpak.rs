@@ -81,6 +81,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::List,
             Capability::Update,
             Capability::Upgrade,
+            Capability::Info,
+            Capability::RequiresElevation,
         ])
     }
 
@@ -305,6 +307,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                     install_path: PathBuf::from("/var/lib/flatpak"),
                     installed_at: String::new(),
                     is_global: true,
+                    kind: None,
                 })
             })
             .collect())
@@ -538,6 +541,7 @@ fn test_parse_flatpak_list_installed_output() {
                     install_path: PathBuf::from("/var/lib/flatpak"),
                     installed_at: String::new(),
                     is_global: true,
+                    kind: None,
                 })
             })
             .collect();
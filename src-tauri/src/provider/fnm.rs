@@ -1,3 +1,4 @@
+use super::node_base::{fetch_node_dist_versions, NODE_DIST_URL};
 use super::traits::*;
 use crate::error::{CogniaError, CogniaResult};
 use crate::platform::{
@@ -5,6 +6,7 @@
     process::{self, ProcessOptions},
 };
 use async_trait::async_trait;
+use reqwest::Client;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
@@ -13,12 +15,14 @@
 /// Written in Rust, faster than nvm
 pub struct FnmProvider {
     fnm_dir: Option<PathBuf>,
+    client: Client,
 }
 
 impl FnmProvider {
     pub fn new() -> Self {
         Self {
             fnm_dir: Self::detect_fnm_dir(),
+            client: crate::platform::proxy::get_client_for_provider("fnm"),
         }
     }
 
@@ -87,6 +91,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::VersionSwitch,
             Capability::MultiVersion,
             Capability::ProjectLocal,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
 
@@ -157,10 +163,10 @@ async fn get_package_info(&self, name: &str) -> CogniaResult<PackageInfo> {
     async fn get_versions(&self, _name: &str) -> CogniaResult<Vec<VersionInfo>> {
         let output = self.run_fnm(&["ls-remote"]).await?;
 
+        // Lines are like "v20.11.0 (Iron)"; keep only the version
         Ok(output
             .lines()
-            .map(|line| line.trim())
-            .filter(|line| !line.is_empty())
+            .filter_map(|line| line.split_whitespace().next())
             .map(|version| VersionInfo {
                 version: version.to_string(),
                 release_date: None,
@@ -170,6 +176,18 @@ async fn get_versions(&self, _name: &str) -> CogniaResult<Vec<VersionInfo>> {
             .collect())
     }
 
+    /// Diff the Node.js release index fnm installs from, honouring
+    /// `FNM_NODE_DIST_MIRROR`, instead of listing every remote version.
+    async fn fetch_versions_incremental(
+        &self,
+        _name: &str,
+        cursor: &VersionSyncCursor,
+    ) -> CogniaResult<VersionFetch> {
+        let dist_url =
+            std::env::var("FNM_NODE_DIST_MIRROR").unwrap_or_else(|_| NODE_DIST_URL.into());
+        fetch_node_dist_versions(&self.client, &dist_url, cursor).await
+    }
+
     async fn install(&self, req: InstallRequest) -> CogniaResult<InstallReceipt> {
         self.install_with_progress(req, None).await
     }
@@ -291,6 +309,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                     install_path: fnm_dir.join("node-versions").join(version),
                     installed_at: String::new(),
                     is_global: true,
+                    kind: None,
                 })
             })
             .collect();
@@ -1,14 +1,82 @@
+use super::install_script::{self, InstallScript};
 use super::traits::*;
+use crate::core::custom_env_type::validate_version;
 use crate::error::{CogniaError, CogniaResult};
 use crate::platform::{
     env::{EnvModifications, Platform},
     process::{self, ProcessOptions},
 };
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
+const FVM_INSTALL_SCRIPT_URL: &str = "https://fvm.app/install.sh";
+
+/// Flutter release channels FVM accepts in place of a version
+pub const FLUTTER_CHANNELS: &[&str] = &["stable", "beta", "dev", "master", "main"];
+
+pub fn is_flutter_channel(name: &str) -> bool {
+    FLUTTER_CHANNELS.contains(&name.trim())
+}
+
+/// `version` trimmed, once it is known not to be a flag or a path
+fn checked_version(version: &str) -> CogniaResult<&str> {
+    let version = version.trim();
+    validate_version(version)?;
+    Ok(version)
+}
+
+/// A Flutter SDK in the FVM cache
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FvmSdk {
+    /// Version or channel name as passed to `fvm install`
+    pub name: String,
+    pub is_channel: bool,
+    /// Channel a release was published on, when FVM reports it
+    pub release_channel: Option<String>,
+    pub flutter_version: Option<String>,
+    pub dart_version: Option<String>,
+    pub directory: Option<String>,
+    /// Whether the SDK has been set up (first `flutter` run completed)
+    pub is_setup: bool,
+}
+
+/// A Flutter release from `fvm releases`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FvmRelease {
+    pub version: String,
+    pub channel: Option<String>,
+    pub release_date: Option<String>,
+    pub dart_sdk_version: Option<String>,
+}
+
+/// The Flutter SDK a project pins through FVM
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FvmProjectStatus {
+    /// Pinned version or channel, `None` when the project has no FVM config
+    pub pinned: Option<String>,
+    pub is_channel: bool,
+    /// `.fvmrc` or legacy `.fvm/fvm_config.json` the pin came from
+    pub config_path: Option<String>,
+    /// Whether the pinned SDK is already in the FVM cache
+    pub installed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FvmDoctorReport {
+    pub fvm_version: Option<String>,
+    pub fvm_path: Option<String>,
+    pub cache_dir: Option<String>,
+    /// `fvm doctor` output; it has no JSON mode
+    pub report: String,
+}
+
 /// FVM - Flutter Version Manager
 ///
 /// Manages Flutter SDK versions using FVM (https://fvm.app).
@@ -96,6 +164,352 @@ async fn fetch_releases_json(&self) -> CogniaResult<Vec<serde_json::Value>> {
             .map_err(|e| CogniaError::Parse(format!("Failed to parse fvm api releases: {}", e)))?;
         Ok(releases)
     }
+
+    /// SDKs in the FVM cache, from `fvm api list` or `fvm list` on older FVM
+    pub async fn list_sdks(&self) -> CogniaResult<Vec<FvmSdk>> {
+        if let Ok(output) = self.run_fvm(&["api", "list", "-c"]).await {
+            if let Some(sdks) = parse_api_list(&output) {
+                return Ok(sdks);
+            }
+        }
+        let output = self.run_fvm(&["list"]).await?;
+        Ok(parse_list_text(&output))
+    }
+
+    /// Flutter releases, newest first, optionally limited to one channel
+    pub async fn list_releases(&self, channel: Option<&str>) -> CogniaResult<Vec<FvmRelease>> {
+        let channel = channel.map(str::trim).filter(|c| !c.is_empty());
+        let mut api_args = vec!["api", "releases", "-c"];
+        if let Some(channel) = channel {
+            api_args.extend(["--filter-channel", channel]);
+        }
+
+        let releases = match self
+            .run_fvm(&api_args)
+            .await
+            .ok()
+            .and_then(|o| parse_api_releases(&o))
+        {
+            Some(releases) => releases,
+            None => {
+                let mut args = vec!["releases"];
+                if let Some(channel) = channel {
+                    args.extend(["--channel", channel]);
+                }
+                parse_releases_text(&self.run_fvm(&args).await?)
+            }
+        };
+
+        Ok(releases
+            .into_iter()
+            .filter(|r| channel.map_or(true, |c| r.channel.as_deref().map_or(true, |rc| rc == c)))
+            .collect())
+    }
+
+    /// Install a Flutter version or channel into the FVM cache
+    pub async fn install_sdk(&self, version: &str) -> CogniaResult<String> {
+        self.run_fvm_long(&["install", checked_version(version)?])
+            .await
+    }
+
+    /// Point a project at an SDK by writing `.fvmrc`. With `pin`, a channel is
+    /// pinned to its current release instead of following the channel.
+    pub async fn use_sdk(
+        &self,
+        project_path: &Path,
+        version: &str,
+        pin: bool,
+    ) -> CogniaResult<String> {
+        let mut args = vec![
+            "use",
+            checked_version(version)?,
+            "--skip-pub-get",
+            "--force",
+        ];
+        if pin {
+            args.push("--pin");
+        }
+        let mut opts = ProcessOptions::new().with_timeout(Duration::from_secs(600));
+        opts.cwd = Some(project_path.to_string_lossy().to_string());
+        let output = process::execute("fvm", &args, Some(opts)).await?;
+        if output.success {
+            Ok(output.stdout)
+        } else {
+            Err(CogniaError::Provider(output.stderr))
+        }
+    }
+
+    pub async fn remove_sdk(&self, version: &str) -> CogniaResult<String> {
+        self.run_fvm(&["remove", checked_version(version)?]).await
+    }
+
+    pub async fn doctor(&self) -> CogniaResult<FvmDoctorReport> {
+        let report = self.run_fvm(&["doctor"]).await?;
+        Ok(FvmDoctorReport {
+            fvm_version: self.get_fvm_version().await.ok(),
+            fvm_path: process::which("fvm").await,
+            cache_dir: self.cache_dir.as_ref().map(|p| p.display().to_string()),
+            report,
+        })
+    }
+
+    /// The FVM pin governing `start_path` and whether it is installed
+    pub async fn project_status(&self, start_path: &Path) -> FvmProjectStatus {
+        let Some((pinned, config_path)) = find_project_pin(start_path).await else {
+            return FvmProjectStatus {
+                pinned: None,
+                is_channel: false,
+                config_path: None,
+                installed: false,
+            };
+        };
+        let installed = self
+            .list_sdks()
+            .await
+            .map(|sdks| sdks.iter().any(|sdk| sdk.name == pinned))
+            .unwrap_or(false);
+        FvmProjectStatus {
+            is_channel: is_flutter_channel(&pinned),
+            pinned: Some(pinned),
+            config_path: Some(config_path.display().to_string()),
+            installed,
+        }
+    }
+
+    /// Download the standalone FVM install script for the user to review;
+    /// pass its hash to [`Self::self_install`] to run it
+    pub async fn install_script_preview(&self) -> CogniaResult<InstallScript> {
+        install_script::fetch("fvm", FVM_INSTALL_SCRIPT_URL).await
+    }
+
+    /// Install FVM itself: `dart pub global activate fvm` when a Dart SDK is
+    /// on PATH, otherwise the standalone install script (macOS/Linux), which
+    /// only runs if it still matches the reviewed `expected_sha256`
+    pub async fn self_install(&self, expected_sha256: Option<&str>) -> CogniaResult<String> {
+        let opts = || ProcessOptions::new().with_timeout(Duration::from_secs(600));
+
+        if process::which("dart").await.is_some() {
+            let output =
+                process::execute("dart", &["pub", "global", "activate", "fvm"], Some(opts()))
+                    .await?;
+            return if output.success {
+                Ok(output.stdout)
+            } else {
+                Err(CogniaError::Installation(output.stderr))
+            };
+        }
+
+        if cfg!(windows) {
+            return Err(CogniaError::Installation(
+                "No Dart SDK found to activate fvm with; install Dart first or run `choco install fvm`"
+                    .into(),
+            ));
+        }
+
+        let expected_sha256 = expected_sha256.ok_or_else(|| {
+            CogniaError::Installation(
+                "No Dart SDK found; review the FVM install script and pass its SHA-256 to run it"
+                    .into(),
+            )
+        })?;
+        let output = install_script::run(
+            "fvm",
+            FVM_INSTALL_SCRIPT_URL,
+            expected_sha256,
+            "bash",
+            &[],
+            Duration::from_secs(600),
+        )
+        .await?;
+        if output.success {
+            Ok(output.stdout)
+        } else {
+            Err(CogniaError::Installation(output.stderr))
+        }
+    }
+}
+
+/// Root FVM keeps the version cache and the global `default` link under
+pub fn fvm_home_dir() -> Option<PathBuf> {
+    FvmProvider::detect_cache_dir().and_then(|versions| versions.parent().map(Path::to_path_buf))
+}
+
+/// Whether `path` is inside the FVM home or a project's `.fvm` link
+pub fn is_in_fvm_dir(path: &Path, fvm_home: &Path) -> bool {
+    let normalize = |p: &Path| {
+        let s = p.to_string_lossy().replace('\\', "/");
+        let s = s.trim_end_matches('/').to_string();
+        if cfg!(windows) {
+            s.to_ascii_lowercase()
+        } else {
+            s
+        }
+    };
+    let home = normalize(fvm_home);
+    let target = normalize(path);
+    target.starts_with(&format!("{}/", home)) || target.contains("/.fvm/")
+}
+
+/// Whether the `tool` (`flutter`, `dart`) resolved from PATH comes from FVM
+/// rather than a system Flutter install
+pub async fn is_fvm_managed(tool: &str) -> bool {
+    let Some(home) = fvm_home_dir() else {
+        return false;
+    };
+    match process::which(tool).await {
+        Some(path) => is_in_fvm_dir(Path::new(&path), &home),
+        None => false,
+    }
+}
+
+/// Nearest `.fvmrc` (or legacy `.fvm/fvm_config.json`) pin at or above `start_path`
+async fn find_project_pin(start_path: &Path) -> Option<(String, PathBuf)> {
+    for dir in start_path.ancestors() {
+        let fvmrc = dir.join(".fvmrc");
+        if let Ok(content) = crate::platform::fs::read_file_string(&fvmrc).await {
+            if let Some(version) = parse_fvmrc(&content).filter(|v| !v.trim().is_empty()) {
+                return Some((version.trim().to_string(), fvmrc));
+            }
+        }
+        let legacy = dir.join(".fvm").join("fvm_config.json");
+        if let Ok(content) = crate::platform::fs::read_file_string(&legacy).await {
+            if let Some(version) = parse_fvm_config_json(&content).filter(|v| !v.trim().is_empty())
+            {
+                return Some((version.trim().to_string(), legacy));
+            }
+        }
+    }
+    None
+}
+
+fn json_str(value: &serde_json::Value, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .find_map(|key| value.get(*key).and_then(|v| v.as_str()))
+        .map(str::to_string)
+        .filter(|s| !s.is_empty())
+}
+
+/// Entries of an `fvm api` response, which is either a bare array or an
+/// object holding the array under `key`
+fn api_entries(output: &str, key: &str) -> Option<Vec<serde_json::Value>> {
+    match serde_json::from_str::<serde_json::Value>(output).ok()? {
+        serde_json::Value::Array(items) => Some(items),
+        serde_json::Value::Object(mut map) => match map.remove(key)? {
+            serde_json::Value::Array(items) => Some(items),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Parse `fvm api list` JSON. `None` when the output is not JSON.
+pub fn parse_api_list(output: &str) -> Option<Vec<FvmSdk>> {
+    Some(
+        api_entries(output, "versions")?
+            .iter()
+            .filter_map(|entry| {
+                let name = json_str(entry, &["name", "version"])?;
+                Some(FvmSdk {
+                    is_channel: is_flutter_channel(&name),
+                    release_channel: json_str(entry, &["releaseFromChannel", "channel"]),
+                    flutter_version: json_str(entry, &["flutterSdkVersion"]),
+                    dart_version: json_str(entry, &["dartSdkVersion"]),
+                    directory: json_str(entry, &["directory"]),
+                    is_setup: entry
+                        .get("isSetup")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false),
+                    name,
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Parse `fvm api releases` JSON. `None` when the output is not JSON.
+pub fn parse_api_releases(output: &str) -> Option<Vec<FvmRelease>> {
+    Some(
+        api_entries(output, "versions")?
+            .iter()
+            .filter_map(|entry| {
+                let version = json_str(entry, &["version", "tag_name"])?;
+                Some(FvmRelease {
+                    version: version.strip_prefix('v').unwrap_or(&version).to_string(),
+                    channel: json_str(entry, &["channel"]),
+                    release_date: json_str(entry, &["release_date", "releaseDate"]),
+                    dart_sdk_version: json_str(entry, &["dart_sdk_version", "dartSdkVersion"]),
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Cells of one row of the box tables FVM prints, skipping borders and
+/// non-table lines
+fn table_cells(line: &str) -> Option<Vec<String>> {
+    let is_sep = |c: char| matches!(c, '│' | '┃' | '|');
+    let line = line.trim();
+    if !line.starts_with(is_sep) {
+        return None;
+    }
+    let cells: Vec<String> = line
+        .trim_matches(is_sep)
+        .split(is_sep)
+        .map(|cell| cell.trim().to_string())
+        .collect();
+    // Header rows and rules have no version-looking first cell
+    let first = cells.first()?;
+    (is_flutter_channel(first) || first.starts_with(|c: char| c.is_ascii_digit())).then_some(cells)
+}
+
+/// Parse `fvm list` text from FVM versions without the `api` command: either
+/// a table or one `<version> [(active)]` per line
+pub fn parse_list_text(output: &str) -> Vec<FvmSdk> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let name = match table_cells(line) {
+                Some(cells) => cells.into_iter().next()?,
+                None => {
+                    let first = line.split_whitespace().next()?;
+                    let looks_like_sdk = is_flutter_channel(first)
+                        || (first.starts_with(|c: char| c.is_ascii_digit()) && first.contains('.'));
+                    if !looks_like_sdk {
+                        return None;
+                    }
+                    first.to_string()
+                }
+            };
+            Some(FvmSdk {
+                is_channel: is_flutter_channel(&name),
+                release_channel: None,
+                flutter_version: None,
+                dart_version: None,
+                directory: None,
+                is_setup: false,
+                name,
+            })
+        })
+        .collect()
+}
+
+/// Parse the `Version | Release Date | Channel` table of `fvm releases`
+pub fn parse_releases_text(output: &str) -> Vec<FvmRelease> {
+    output
+        .lines()
+        .filter_map(table_cells)
+        .filter_map(|cells| {
+            let mut cells = cells.into_iter();
+            let version = cells.next()?;
+            let non_empty = |s: Option<String>| s.filter(|s| !s.is_empty());
+            Some(FvmRelease {
+                version: version.strip_prefix('v').unwrap_or(&version).to_string(),
+                release_date: non_empty(cells.next()),
+                channel: non_empty(cells.next()),
+                dart_sdk_version: None,
+            })
+        })
+        .collect()
 }
 
 impl Default for FvmProvider {
@@ -140,6 +554,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::List,
             Capability::VersionSwitch,
             Capability::MultiVersion,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
 
@@ -243,7 +659,7 @@ async fn get_versions(&self, _name: &str) -> CogniaResult<Vec<VersionInfo>> {
     }
 
     async fn install(&self, req: InstallRequest) -> CogniaResult<InstallReceipt> {
-        let version = req.version.as_deref().unwrap_or("stable");
+        let version = checked_version(req.version.as_deref().unwrap_or("stable"))?;
 
         self.run_fvm_long(&["install", version]).await?;
 
@@ -265,7 +681,8 @@ async fn uninstall(&self, req: UninstallRequest) -> CogniaResult<()> {
             .version
             .ok_or_else(|| CogniaError::Provider("Version required for uninstall".into()))?;
 
-        self.run_fvm(&["remove", &version]).await?;
+        self.run_fvm(&["remove", checked_version(&version)?])
+            .await?;
         Ok(())
     }
 
@@ -303,6 +720,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                 install_path: cache_dir.join(&version),
                 installed_at: String::new(),
                 is_global: true,
+                kind: None,
             });
         }
 
@@ -399,20 +817,13 @@ async fn get_current_version(&self) -> CogniaResult<Option<String>> {
     }
 
     async fn set_global_version(&self, version: &str) -> CogniaResult<()> {
-        self.run_fvm(&["global", version]).await?;
+        self.run_fvm(&["global", checked_version(version)?]).await?;
         Ok(())
     }
 
     async fn set_local_version(&self, project_path: &Path, version: &str) -> CogniaResult<()> {
-        let mut opts = ProcessOptions::new().with_timeout(Duration::from_secs(120));
-        opts.cwd = Some(project_path.to_string_lossy().to_string());
-        let output =
-            process::execute("fvm", &["use", version, "--skip-pub-get"], Some(opts)).await?;
-        if output.success {
-            Ok(())
-        } else {
-            Err(CogniaError::Provider(output.stderr))
-        }
+        self.use_sdk(project_path, version, false).await?;
+        Ok(())
     }
 
     async fn detect_version(&self, start_path: &Path) -> CogniaResult<Option<VersionDetection>> {
@@ -554,6 +965,16 @@ async fn is_package_installed(&self, name: &str) -> CogniaResult<bool> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_checked_version_rejects_flags_and_paths() {
+        assert_eq!(checked_version(" 3.19.0 ").unwrap(), "3.19.0");
+        assert_eq!(checked_version("stable").unwrap(), "stable");
+        assert_eq!(checked_version("3.19.0@beta").unwrap(), "3.19.0@beta");
+        for version in ["--help", "../flutter", "a/b", "", "3.19.0 --force"] {
+            assert!(checked_version(version).is_err(), "{}", version);
+        }
+    }
+
     #[test]
     fn test_parse_fvmrc() {
         let content = r#"{"flutter": "3.19.0"}"#;
@@ -728,4 +1149,89 @@ fn test_default_creates_provider() {
         let provider = FvmProvider::default();
         assert_eq!(provider.id(), "fvm");
     }
+
+    #[test]
+    fn test_parse_api_list_object_and_array() {
+        let object = r#"{"size":"1.2 GB","versions":[
+            {"name":"3.19.0","directory":"/home/u/fvm/versions/3.19.0","releaseFromChannel":"stable",
+             "flutterSdkVersion":"3.19.0","dartSdkVersion":"3.3.0","isSetup":true},
+            {"name":"beta","directory":"/home/u/fvm/versions/beta","isSetup":false}
+        ]}"#;
+        let sdks = parse_api_list(object).unwrap();
+        assert_eq!(sdks.len(), 2);
+        assert_eq!(sdks[0].name, "3.19.0");
+        assert_eq!(sdks[0].dart_version.as_deref(), Some("3.3.0"));
+        assert_eq!(sdks[0].release_channel.as_deref(), Some("stable"));
+        assert!(sdks[0].is_setup);
+        assert!(sdks[1].is_channel);
+
+        let array = r#"[{"name":"3.16.0"}]"#;
+        assert_eq!(parse_api_list(array).unwrap()[0].name, "3.16.0");
+
+        assert!(parse_api_list("Could not find a command named \"api\".").is_none());
+    }
+
+    #[test]
+    fn test_parse_list_text_table_and_plain() {
+        let table = "Cache directory:  /home/u/fvm/versions\n\
+┌─────────┬─────────┬─────────────────┐\n\
+│ Version │ Channel │ Flutter Version │\n\
+├─────────┼─────────┼─────────────────┤\n\
+│ stable  │ stable  │ 3.19.3          │\n\
+│ 3.16.0  │ stable  │ 3.16.0          │\n\
+└─────────┴─────────┴─────────────────┘\n";
+        let names: Vec<_> = parse_list_text(table).into_iter().map(|s| s.name).collect();
+        assert_eq!(names, vec!["stable", "3.16.0"]);
+
+        let plain = "Cache Directory:  /home/u/fvm/versions\n\n3.19.0 (active)\nbeta\n";
+        let sdks = parse_list_text(plain);
+        assert_eq!(sdks.len(), 2);
+        assert_eq!(sdks[0].name, "3.19.0");
+        assert!(sdks[1].is_channel);
+    }
+
+    #[test]
+    fn test_parse_releases() {
+        let json = r#"{"versions":[
+            {"version":"3.19.3","channel":"stable","release_date":"2024-03-06","dart_sdk_version":"3.3.1"},
+            {"version":"v3.20.0-1.2.pre","channel":"beta","release_date":"2024-02-29"}
+        ]}"#;
+        let releases = parse_api_releases(json).unwrap();
+        assert_eq!(releases[0].dart_sdk_version.as_deref(), Some("3.3.1"));
+        assert_eq!(releases[1].version, "3.20.0-1.2.pre");
+
+        let text = "│ Version │ Release Date │ Channel │\n│ 3.19.3  │ Mar 6, 2024  │ stable  │\n";
+        let releases = parse_releases_text(text);
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].channel.as_deref(), Some("stable"));
+        assert_eq!(releases[0].release_date.as_deref(), Some("Mar 6, 2024"));
+    }
+
+    #[test]
+    fn test_is_in_fvm_dir() {
+        let home = Path::new("/home/u/fvm");
+        assert!(is_in_fvm_dir(
+            Path::new("/home/u/fvm/default/bin/flutter"),
+            home
+        ));
+        assert!(is_in_fvm_dir(
+            Path::new("/work/app/.fvm/flutter_sdk/bin/flutter"),
+            home
+        ));
+        assert!(!is_in_fvm_dir(Path::new("/opt/flutter/bin/flutter"), home));
+        assert!(!is_in_fvm_dir(Path::new("/home/u/fvmx/bin/flutter"), home));
+    }
+
+    #[tokio::test]
+    async fn test_find_project_pin_walks_up() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(root.path().join(".fvmrc"), r#"{"flutter": "beta"}"#).unwrap();
+        let nested = root.path().join("packages").join("app");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        let (pinned, path) = find_project_pin(&nested).await.unwrap();
+        assert_eq!(pinned, "beta");
+        assert_eq!(path, root.path().join(".fvmrc"));
+        assert!(is_flutter_channel(&pinned));
+    }
 }
@@ -7,7 +7,7 @@
 use crate::resolver::{Dependency, VersionConstraint};
 use async_trait::async_trait;
 use std::collections::HashSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 /// RubyGems - The Ruby community's gem hosting service
@@ -72,7 +72,7 @@ async fn query_installed_version(&self, name: &str) -> CogniaResult<String> {
     /// Fetch gem info from rubygems.org API
     async fn fetch_rubygems_info(&self, name: &str) -> CogniaResult<serde_json::Value> {
         let url = format!("https://rubygems.org/api/v1/gems/{}.json", name);
-        let client = crate::platform::proxy::get_shared_client();
+        let client = crate::platform::proxy::get_client_for_provider("gem");
 
         let resp = client
             .get(&url)
@@ -96,7 +96,7 @@ async fn fetch_rubygems_info(&self, name: &str) -> CogniaResult<serde_json::Valu
     /// Fetch gem versions from rubygems.org API
     async fn fetch_rubygems_versions(&self, name: &str) -> CogniaResult<Vec<VersionInfo>> {
         let url = format!("https://rubygems.org/api/v1/versions/{}.json", name);
-        let client = crate::platform::proxy::get_shared_client();
+        let client = crate::platform::proxy::get_client_for_provider("gem");
 
         let resp = client
             .get(&url)
@@ -136,6 +136,14 @@ async fn fetch_rubygems_versions(&self, name: &str) -> CogniaResult<Vec<VersionI
     }
 }
 
+/// Version of `name` from `gem install` output ("Successfully installed rake-13.2.1")
+fn parse_installed_gem_version(out: &str, name: &str) -> Option<String> {
+    let prefix = format!("Successfully installed {}-", name);
+    out.lines()
+        .find_map(|line| line.trim().strip_prefix(&prefix))
+        .map(|version| version.trim().to_string())
+}
+
 impl Default for GemProvider {
     fn default() -> Self {
         Self::new()
@@ -158,6 +166,9 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::List,
             Capability::Update,
             Capability::MultiVersion,
+            Capability::Info,
+            Capability::Versions,
+            Capability::SupportsPrefix,
         ])
     }
     fn supported_platforms(&self) -> Vec<Platform> {
@@ -189,7 +200,7 @@ async fn search(
             "https://rubygems.org/api/v1/search.json?query={}&page=1",
             query
         );
-        let client = crate::platform::proxy::get_shared_client();
+        let client = crate::platform::proxy::get_client_for_provider("gem");
 
         let resp = client
             .get(&url)
@@ -397,6 +408,47 @@ async fn install(&self, req: InstallRequest) -> CogniaResult<InstallReceipt> {
         })
     }
 
+    /// Gems, their dependencies and bin stubs all go under the prefix; the
+    /// activation env points `GEM_HOME`/`GEM_PATH` at it
+    async fn install_to_prefix(
+        &self,
+        req: InstallRequest,
+        prefix: &Path,
+    ) -> CogniaResult<InstallReceipt> {
+        let install_dir = prefix.to_string_lossy().to_string();
+        let bin_dir = prefix.join("bin").to_string_lossy().to_string();
+        let mut args = vec![
+            "install",
+            &req.name,
+            "--no-document",
+            "--install-dir",
+            &install_dir,
+            "--bindir",
+            &bin_dir,
+        ];
+
+        let version_flag;
+        if let Some(v) = &req.version {
+            version_flag = format!("--version={}", v);
+            args.push(&version_flag);
+        }
+
+        let out = self.run_gem(&args).await?;
+        let actual_version = parse_installed_gem_version(&out, &req.name)
+            .unwrap_or_else(|| req.version.clone().unwrap_or_else(|| "unknown".into()));
+
+        Ok(InstallReceipt {
+            install_path: prefix
+                .join("gems")
+                .join(format!("{}-{}", req.name, actual_version)),
+            name: req.name,
+            version: actual_version,
+            provider: self.id().into(),
+            files: vec![],
+            installed_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
     async fn get_installed_version(&self, name: &str) -> CogniaResult<Option<String>> {
         match self.query_installed_version(name).await {
             Ok(v) => Ok(Some(v)),
@@ -454,6 +506,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                     install_path: gem_home.join("gems").join(format!("{}-{}", name, version)),
                     installed_at: String::new(),
                     is_global: true,
+                    kind: None,
                 })
             })
             .collect())
@@ -536,6 +589,16 @@ fn test_capabilities() {
         assert!(caps.contains(&Capability::MultiVersion));
     }
 
+    #[test]
+    fn test_parse_installed_gem_version() {
+        let out = "Successfully installed minitest-5.25.1\nSuccessfully installed rake-13.2.1\n2 gems installed\n";
+        assert_eq!(
+            parse_installed_gem_version(out, "rake"),
+            Some("13.2.1".to_string())
+        );
+        assert_eq!(parse_installed_gem_version(out, "rails"), None);
+    }
+
     #[test]
     fn test_supported_platforms() {
         let provider = GemProvider::new();
@@ -489,6 +489,61 @@ pub struct GitRebaseTodoItem {
     pub message: String,
 }
 
+/// A commit in an interactive rebase plan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitRebasePlanEntry {
+    /// pick | reword | squash | fixup | drop
+    pub action: String,
+    pub hash: String,
+    #[serde(default)]
+    pub short_hash: String,
+    #[serde(default)]
+    pub subject: String,
+    #[serde(default)]
+    pub author_name: String,
+    #[serde(default)]
+    pub date: String,
+    /// Whether the commit is already on the upstream tracking branch
+    #[serde(default)]
+    pub published: bool,
+    /// Replacement message, required for `reword`
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitRebasePlan {
+    pub base: String,
+    pub upstream: Option<String>,
+    pub entries: Vec<GitRebasePlanEntry>,
+    #[serde(default)]
+    pub has_published: bool,
+    #[serde(default)]
+    pub dirty: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitRebaseExecuteOptions {
+    /// Stash local changes for the duration of the rebase
+    #[serde(default)]
+    pub autostash: bool,
+    /// Allow rewriting commits that are already on the upstream branch
+    #[serde(default)]
+    pub allow_published: bool,
+}
+
+/// Rebase state plus the files that need resolving before it can continue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitRebaseStatus {
+    pub in_progress: bool,
+    pub state: GitMergeRebaseState,
+    pub conflicted_files: Vec<GitStatusFile>,
+}
+
 // ============================================================================
 // Clone options & progress types
 // ============================================================================
@@ -929,6 +984,91 @@ pub fn parse_status_porcelain(output: &str) -> (u32, u32, u32) {
 }
 
 /// Parse `git status --porcelain` output into file-level details
+const REBASE_PLAN_ACTIONS: &[&str] = &["pick", "reword", "squash", "fixup", "drop"];
+const REBASE_PLAN_DIR: &str = "cognia-rebase";
+
+/// Whether a porcelain status entry is an unmerged (conflicted) path
+pub fn is_conflicted_status(file: &GitStatusFile) -> bool {
+    matches!(
+        (file.index_status.as_str(), file.worktree_status.as_str()),
+        ("U", _) | (_, "U") | ("A", "A") | ("D", "D")
+    )
+}
+
+/// Quote a path for the `sh -c` git uses to run editors
+fn shell_quote_path(path: &Path) -> String {
+    let raw = path.to_string_lossy().replace('\\', "/");
+    format!("'{}'", raw.replace('\'', "'\\''"))
+}
+
+/// Build the rebase todo for a plan. `reword` becomes a `pick` followed by an
+/// `exec git commit --amend -F <file>` so no interactive editor is needed;
+/// returns the todo text and the message files that must exist beforehand.
+pub fn build_rebase_plan_todo(
+    entries: &[GitRebasePlanEntry],
+    message_dir: &Path,
+) -> CogniaResult<(String, Vec<(PathBuf, String)>)> {
+    let mut lines = Vec::with_capacity(entries.len());
+    let mut messages = Vec::new();
+    let mut has_target = false;
+
+    for (index, entry) in entries.iter().enumerate() {
+        let action = entry.action.trim().to_lowercase();
+        if !REBASE_PLAN_ACTIONS.contains(&action.as_str()) {
+            return Err(CogniaError::Provider(format!(
+                "git: unsupported rebase action '{}'",
+                entry.action
+            )));
+        }
+        if entry.hash.trim().is_empty() {
+            return Err(CogniaError::Provider(
+                "git: rebase plan entry is missing a commit hash".into(),
+            ));
+        }
+        let hash = entry.hash.trim();
+
+        match action.as_str() {
+            "squash" | "fixup" if !has_target => {
+                return Err(CogniaError::Provider(format!(
+                    "git: cannot {} commit {} without a previous commit",
+                    action,
+                    &hash[..7.min(hash.len())]
+                )));
+            }
+            "reword" => {
+                let message = entry
+                    .message
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|m| !m.is_empty())
+                    .ok_or_else(|| {
+                        CogniaError::Provider(format!(
+                            "git: reword of {} requires a commit message",
+                            &hash[..7.min(hash.len())]
+                        ))
+                    })?;
+                let file = message_dir.join(format!("message-{}.txt", index));
+                lines.push(format!("pick {}", hash));
+                lines.push(format!(
+                    "exec git commit --amend --allow-empty -F {}",
+                    shell_quote_path(&file)
+                ));
+                messages.push((file, format!("{}\n", message)));
+                has_target = true;
+                continue;
+            }
+            _ => {}
+        }
+
+        if action != "drop" {
+            has_target = true;
+        }
+        lines.push(format!("{} {}", action, hash));
+    }
+
+    Ok((lines.join("\n") + "\n", messages))
+}
+
 pub fn parse_status_files(output: &str) -> Vec<GitStatusFile> {
     output
         .lines()
@@ -3379,17 +3519,24 @@ pub async fn rebase(&self, path: &str, onto: &str) -> CogniaResult<String> {
     /// Abort an in-progress rebase
     pub async fn rebase_abort(&self, path: &str) -> CogniaResult<String> {
         run_git_in(path, &["rebase", "--abort"]).await?;
+        self.cleanup_rebase_plan_dir(path).await;
         Ok("Rebase aborted".into())
     }
 
-    /// Continue rebase after resolving conflicts
+    /// Continue rebase after resolving conflicts, keeping the prepared
+    /// message instead of opening an editor
     pub async fn rebase_continue(&self, path: &str) -> CogniaResult<String> {
-        run_git_in(path, &["rebase", "--continue"]).await
+        let opts = make_install_opts().with_env("GIT_EDITOR", "true");
+        let output = run_git_with_opts(&["-C", path, "rebase", "--continue"], opts).await?;
+        self.cleanup_rebase_plan_dir(path).await;
+        Ok(output)
     }
 
     /// Skip current commit during rebase
     pub async fn rebase_skip(&self, path: &str) -> CogniaResult<String> {
-        run_git_in(path, &["rebase", "--skip"]).await
+        let output = run_git_in(path, &["rebase", "--skip"]).await?;
+        self.cleanup_rebase_plan_dir(path).await;
+        Ok(output)
     }
 
     /// Squash last N commits into one
@@ -3862,6 +4009,251 @@ pub async fn start_interactive_rebase(
         }
     }
 
+    /// Commits between `base` and HEAD with default `pick` actions, flagged when
+    /// they are already on the upstream tracking branch
+    pub async fn rebase_plan(&self, path: &str, base: &str) -> CogniaResult<GitRebasePlan> {
+        let base_commit = format!("{}^{{commit}}", base);
+        run_git_in(path, &["rev-parse", "--verify", "--quiet", &base_commit])
+            .await
+            .map_err(|_| CogniaError::Provider(format!("git: unknown base revision '{}'", base)))?;
+
+        let format_arg = format!("--format=%H{sep}%h{sep}%an{sep}%aI{sep}%s", sep = FIELD_SEP);
+        let range = format!("{}..HEAD", base);
+        let output = run_git_in(
+            path,
+            &["log", "--reverse", "--no-merges", &format_arg, &range],
+        )
+        .await?;
+
+        let (upstream, published) = self.published_in_range(path, base).await;
+        let entries: Vec<GitRebasePlanEntry> = output
+            .lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.trim().splitn(5, FIELD_SEP).collect();
+                if fields.len() < 5 {
+                    return None;
+                }
+                Some(GitRebasePlanEntry {
+                    action: "pick".to_string(),
+                    hash: fields[0].to_string(),
+                    short_hash: fields[1].to_string(),
+                    author_name: fields[2].to_string(),
+                    date: fields[3].to_string(),
+                    subject: fields[4].to_string(),
+                    published: published.contains(fields[0]),
+                    message: None,
+                })
+            })
+            .collect();
+        if entries.is_empty() {
+            return Err(CogniaError::Provider(format!(
+                "git: no commits between {} and HEAD",
+                base
+            )));
+        }
+
+        Ok(GitRebasePlan {
+            base: base.to_string(),
+            upstream,
+            has_published: entries.iter().any(|e| e.published),
+            dirty: self.has_tracked_changes(path).await?,
+            entries,
+        })
+    }
+
+    /// Run an interactive rebase from a plan. Stops on conflicts and reports
+    /// them so the rebase can be resumed with continue/skip or aborted.
+    pub async fn rebase_execute(
+        &self,
+        path: &str,
+        plan: &GitRebasePlan,
+        options: &GitRebaseExecuteOptions,
+    ) -> CogniaResult<GitRebaseStatus> {
+        let state = self.get_merge_rebase_state(path).await?;
+        if state.state != "none" {
+            return Err(CogniaError::Provider(format!(
+                "git: cannot rebase while {} is in progress",
+                state.state.replace('_', "-")
+            )));
+        }
+        if !options.autostash && self.has_tracked_changes(path).await? {
+            return Err(CogniaError::Provider(
+                "git: working tree has uncommitted changes; commit, stash or enable autostash"
+                    .into(),
+            ));
+        }
+
+        // Re-read the range so a stale plan cannot silently drop commits
+        let current = self.rebase_plan(path, &plan.base).await?;
+        let current_hashes: HashSet<&str> =
+            current.entries.iter().map(|e| e.hash.as_str()).collect();
+        let plan_hashes: HashSet<&str> = plan.entries.iter().map(|e| e.hash.trim()).collect();
+        if current_hashes != plan_hashes || plan_hashes.len() != plan.entries.len() {
+            return Err(CogniaError::Provider(
+                "git: rebase plan is out of date; reload it and try again".into(),
+            ));
+        }
+        if current.has_published && !options.allow_published {
+            let upstream = current.upstream.as_deref().unwrap_or("upstream");
+            return Err(CogniaError::Provider(format!(
+                "git: plan rewrites commits already on {}; choose a newer base or allow published commits",
+                upstream
+            )));
+        }
+
+        let work_dir = self.rebase_plan_dir(path).await?;
+        let _ = tokio::fs::remove_dir_all(&work_dir).await;
+        let (todo, messages) = build_rebase_plan_todo(&plan.entries, &work_dir)?;
+        let write_err = |e: std::io::Error| {
+            CogniaError::Provider(format!("Failed to write rebase plan: {}", e))
+        };
+        tokio::fs::create_dir_all(&work_dir)
+            .await
+            .map_err(write_err)?;
+        for (file, message) in &messages {
+            tokio::fs::write(file, message).await.map_err(write_err)?;
+        }
+        let todo_path = work_dir.join("git-rebase-todo");
+        tokio::fs::write(&todo_path, todo)
+            .await
+            .map_err(write_err)?;
+
+        // git runs editors through `sh -c`, so a plain `cp` works everywhere
+        // git does (Git for Windows ships its own shell)
+        let opts = make_install_opts()
+            .with_env(
+                "GIT_SEQUENCE_EDITOR",
+                format!("cp {}", shell_quote_path(&todo_path)),
+            )
+            .with_env("GIT_EDITOR", "true");
+        let mut args = vec!["-C", path, "rebase", "-i"];
+        if options.autostash {
+            args.push("--autostash");
+        }
+        args.push(&plan.base);
+        let result = process::execute("git", &args, Some(opts))
+            .await
+            .map_err(|e| CogniaError::Provider(format!("git rebase -i: {}", e)))?;
+
+        let status = self.rebase_status(path).await?;
+        if result.success || status.in_progress {
+            if !status.in_progress {
+                let _ = tokio::fs::remove_dir_all(&work_dir).await;
+            }
+            return Ok(status);
+        }
+
+        let _ = tokio::fs::remove_dir_all(&work_dir).await;
+        let err = if result.stderr.trim().is_empty() {
+            result.stdout.trim().to_string()
+        } else {
+            result.stderr.trim().to_string()
+        };
+        Err(CogniaError::Provider(format!("git rebase -i: {}", err)))
+    }
+
+    /// Current rebase/merge state with the conflicted files from `git status`
+    pub async fn rebase_status(&self, path: &str) -> CogniaResult<GitRebaseStatus> {
+        let state = self.get_merge_rebase_state(path).await?;
+        let conflicted_files = self
+            .get_status(path)
+            .await?
+            .into_iter()
+            .filter(is_conflicted_status)
+            .collect();
+        Ok(GitRebaseStatus {
+            in_progress: state.state != "none",
+            state,
+            conflicted_files,
+        })
+    }
+
+    /// Amend HEAD, either keeping its message (`no_edit`) or replacing it
+    pub async fn commit_amend(
+        &self,
+        path: &str,
+        message: Option<&str>,
+        no_edit: bool,
+    ) -> CogniaResult<String> {
+        let message = message.map(str::trim).filter(|m| !m.is_empty());
+        let mut args = vec!["commit", "--amend"];
+        match (message, no_edit) {
+            (Some(_), true) => {
+                return Err(CogniaError::Provider(
+                    "git: a new message cannot be combined with no-edit".into(),
+                ))
+            }
+            (Some(message), false) => {
+                args.push("-m");
+                args.push(message);
+            }
+            (None, true) => args.push("--no-edit"),
+            (None, false) => {
+                return Err(CogniaError::Provider(
+                    "git: amend requires a message or no-edit".into(),
+                ))
+            }
+        }
+        run_git_in(path, &args).await
+    }
+
+    /// Upstream tracking branch and the commits in `base..HEAD` already on it
+    async fn published_in_range(
+        &self,
+        path: &str,
+        base: &str,
+    ) -> (Option<String>, HashSet<String>) {
+        let Ok(upstream) = run_git_in(
+            path,
+            &[
+                "rev-parse",
+                "--abbrev-ref",
+                "--symbolic-full-name",
+                "@{upstream}",
+            ],
+        )
+        .await
+        else {
+            return (None, HashSet::new());
+        };
+        let range = format!("{}..HEAD", base);
+        let all = run_git_in(path, &["rev-list", &range])
+            .await
+            .unwrap_or_default();
+        let unpublished = run_git_in(path, &["rev-list", &range, "--not", &upstream])
+            .await
+            .unwrap_or_default();
+        let unpublished: HashSet<&str> = unpublished.lines().map(str::trim).collect();
+        let published = all
+            .lines()
+            .map(str::trim)
+            .filter(|hash| !hash.is_empty() && !unpublished.contains(hash))
+            .map(str::to_string)
+            .collect();
+        (Some(upstream), published)
+    }
+
+    async fn has_tracked_changes(&self, path: &str) -> CogniaResult<bool> {
+        let output = run_git_in(path, &["status", "--porcelain", "--untracked-files=no"]).await?;
+        Ok(!output.trim().is_empty())
+    }
+
+    async fn rebase_plan_dir(&self, path: &str) -> CogniaResult<PathBuf> {
+        let git_dir = run_git_in(path, &["rev-parse", "--git-dir"]).await?;
+        Ok(Path::new(path).join(git_dir.trim()).join(REBASE_PLAN_DIR))
+    }
+
+    /// Remove reword messages left by `rebase_execute` once no rebase is running
+    async fn cleanup_rebase_plan_dir(&self, path: &str) {
+        let idle = self
+            .get_merge_rebase_state(path)
+            .await
+            .is_ok_and(|state| state.state == "none");
+        if let (true, Ok(dir)) = (idle, self.rebase_plan_dir(path).await) {
+            let _ = tokio::fs::remove_dir_all(dir).await;
+        }
+    }
+
     // ========================================================================
     // Git bisect
     // ========================================================================
@@ -4095,6 +4487,7 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::Uninstall,
             Capability::Update,
             Capability::List,
+            Capability::Info,
         ])
     }
 
@@ -4277,6 +4670,7 @@ async fn list_installed(
                 install_path: PathBuf::from(path),
                 installed_at: String::new(),
                 is_global: true,
+                kind: None,
             }])
         } else {
             Ok(vec![])
@@ -5515,4 +5909,75 @@ fn test_rebase_todo_item_long_hash() {
         );
         assert_eq!(formatted, "reword abc1234 long hash");
     }
+
+    fn plan_entry(action: &str, hash: &str, message: Option<&str>) -> GitRebasePlanEntry {
+        GitRebasePlanEntry {
+            action: action.into(),
+            hash: hash.into(),
+            short_hash: hash[..7].into(),
+            subject: "subject".into(),
+            author_name: "dev".into(),
+            date: "2026-01-01T00:00:00Z".into(),
+            published: false,
+            message: message.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_build_rebase_plan_todo() {
+        let dir = Path::new("/repo/.git/cognia-rebase");
+        let entries = vec![
+            plan_entry("pick", "aaaaaaa1", None),
+            plan_entry("reword", "bbbbbbb2", Some("New message")),
+            plan_entry("squash", "ccccccc3", None),
+            plan_entry("drop", "ddddddd4", None),
+        ];
+        let (todo, messages) = build_rebase_plan_todo(&entries, dir).unwrap();
+        assert_eq!(
+            todo,
+            "pick aaaaaaa1\n\
+             pick bbbbbbb2\n\
+             exec git commit --amend --allow-empty -F '/repo/.git/cognia-rebase/message-1.txt'\n\
+             squash ccccccc3\n\
+             drop ddddddd4\n"
+        );
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].0, dir.join("message-1.txt"));
+        assert_eq!(messages[0].1, "New message\n");
+    }
+
+    #[test]
+    fn test_build_rebase_plan_todo_rejects_invalid_plans() {
+        let dir = Path::new("/tmp");
+        let leading_squash = vec![
+            plan_entry("drop", "aaaaaaa1", None),
+            plan_entry("fixup", "bbbbbbb2", None),
+        ];
+        assert!(build_rebase_plan_todo(&leading_squash, dir).is_err());
+
+        let empty_reword = vec![plan_entry("reword", "aaaaaaa1", Some("  "))];
+        assert!(build_rebase_plan_todo(&empty_reword, dir).is_err());
+
+        let unknown = vec![plan_entry("edit", "aaaaaaa1", None)];
+        assert!(build_rebase_plan_todo(&unknown, dir).is_err());
+    }
+
+    #[test]
+    fn test_is_conflicted_status() {
+        let files = parse_status_files("UU both.txt\nAA added.txt\nM  staged.txt\nDU gone.txt");
+        let conflicted: Vec<&str> = files
+            .iter()
+            .filter(|f| is_conflicted_status(f))
+            .map(|f| f.path.as_str())
+            .collect();
+        assert_eq!(conflicted, vec!["both.txt", "added.txt", "gone.txt"]);
+    }
+
+    #[test]
+    fn test_shell_quote_path() {
+        assert_eq!(
+            shell_quote_path(Path::new("/tmp/it's here")),
+            "'/tmp/it'\\''s here'"
+        );
+    }
 }
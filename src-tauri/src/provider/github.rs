@@ -1,14 +1,17 @@
+use super::rate_limit::{ConditionalClient, RateLimitPolicy, RateLimitStatus};
 use super::traits::*;
-use crate::download::{AssetLike, AssetPicker, LibcType};
+use crate::download::{AssetLike, AssetPicker};
 use crate::error::{CogniaError, CogniaResult};
 use crate::platform::{
-    env::{current_arch, current_platform, detect_libc, Architecture, Platform},
+    env::{current_arch, current_platform, Architecture, Platform},
     fs,
     network::HttpClient,
 };
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 const GITHUB_API: &str = "https://api.github.com";
 const GITHUB_API_VERSION: &str = "2022-11-28";
@@ -16,20 +19,44 @@
 pub struct GitHubProvider {
     client: HttpClient,
     token: Option<String>,
+    api: ConditionalClient,
+    last_from_cache: AtomicBool,
 }
 
 impl GitHubProvider {
     pub fn new() -> Self {
         let token = std::env::var("GITHUB_TOKEN").ok();
+        let api = ConditionalClient::new("github").with_token(token.as_deref());
         Self {
-            client: HttpClient::new(),
+            client: HttpClient::for_provider("github"),
             token,
+            api,
+            last_from_cache: AtomicBool::new(false),
         }
     }
 
+    /// Revalidate API responses with ETags kept in the metadata cache, so
+    /// unchanged listings cost no quota
+    pub fn with_cache_dir(mut self, cache_dir: Option<PathBuf>) -> Self {
+        self.api = self.api.with_cache_dir(cache_dir);
+        self
+    }
+
+    /// Queue (the default) or fail fast once the rate limit is exhausted
+    pub fn with_rate_limit_policy(mut self, policy: RateLimitPolicy) -> Self {
+        self.api = self.api.with_policy(policy);
+        self
+    }
+
+    /// Whether the last API call was answered from the conditional cache
+    pub fn last_response_from_cache(&self) -> bool {
+        self.last_from_cache.load(Ordering::Relaxed)
+    }
+
     /// Create a provider with an explicit token (from settings)
     pub fn with_token(mut self, token: Option<String>) -> Self {
         if token.is_some() {
+            self.api = self.api.with_token(token.as_deref());
             self.token = token;
         }
         self
@@ -48,10 +75,27 @@ fn build_request_options(&self) -> crate::platform::network::RequestOptions {
     async fn api_get<T: for<'de> Deserialize<'de>>(&self, path: &str) -> CogniaResult<T> {
         let url = format!("{}{}", GITHUB_API, path);
         let opts = self.build_request_options();
+        let resp = self.api.get_json(&self.client, &url, opts).await?;
+        self.last_from_cache
+            .store(resp.from_cache, Ordering::Relaxed);
+        Ok(resp.data)
+    }
+
+    /// Refresh the recorded quota. `/rate_limit` does not count against the
+    /// quota, so it bypasses the queue.
+    pub async fn refresh_rate_limit(&self) -> CogniaResult<()> {
+        let url = format!("{}/rate_limit", GITHUB_API);
+        let opts = self.build_request_options();
         let resp = self.client.get_with_options(&url, Some(opts)).await?;
-        resp.json()
-            .await
-            .map_err(|e| CogniaError::Network(e.to_string()))
+        super::rate_limit::record_headers(&self.api.bucket(&url), resp.headers());
+        Ok(())
+    }
+
+    /// The core quota last seen for this provider's token
+    pub fn rate_limit_status(&self) -> Option<RateLimitStatus> {
+        super::rate_limit::rate_limit_status(
+            &self.api.bucket(&format!("{}/rate_limit", GITHUB_API)),
+        )
     }
 
     /// Fetch repository metadata (description, stars, license, etc.)
@@ -222,20 +266,7 @@ fn match_asset<'a>(
         platform: Platform,
         arch: Architecture,
     ) -> Option<&'a GitHubAsset> {
-        let mut picker = AssetPicker::new(platform, arch);
-
-        // Add libc detection for Linux
-        if platform == Platform::Linux {
-            let libc = detect_libc();
-            let libc_type = match libc.as_str() {
-                "musl" => LibcType::Musl,
-                "glibc" => LibcType::Glibc,
-                _ => LibcType::Unknown,
-            };
-            picker = picker.with_libc(libc_type);
-        }
-
-        picker.pick_best(assets)
+        AssetPicker::for_target(platform, arch).pick_best(assets)
     }
 }
 
@@ -345,7 +376,13 @@ fn display_name(&self) -> &str {
         "GitHub Releases"
     }
     fn capabilities(&self) -> HashSet<Capability> {
-        HashSet::from([Capability::Install, Capability::Search, Capability::List])
+        HashSet::from([
+            Capability::Install,
+            Capability::Search,
+            Capability::List,
+            Capability::Info,
+            Capability::Versions,
+        ])
     }
     fn supported_platforms(&self) -> Vec<Platform> {
         vec![Platform::Linux, Platform::MacOS, Platform::Windows]
@@ -592,6 +629,8 @@ fn test_get_download_headers_no_token() {
         let provider = GitHubProvider {
             client: HttpClient::new(),
             token: None,
+            api: ConditionalClient::new("github"),
+            last_from_cache: AtomicBool::new(false),
         };
         let headers = provider.get_download_headers();
         assert!(headers.contains_key("X-GitHub-Api-Version"));
@@ -603,6 +642,8 @@ fn test_get_download_headers_with_token() {
         let provider = GitHubProvider {
             client: HttpClient::new(),
             token: Some("ghp_test123".to_string()),
+            api: ConditionalClient::new("github"),
+            last_from_cache: AtomicBool::new(false),
         };
         let headers = provider.get_download_headers();
         assert!(headers.contains_key("Authorization"));
@@ -615,12 +656,16 @@ fn test_has_token() {
         let p1 = GitHubProvider {
             client: HttpClient::new(),
             token: None,
+            api: ConditionalClient::new("github"),
+            last_from_cache: AtomicBool::new(false),
         };
         assert!(!p1.has_token());
 
         let p2 = GitHubProvider {
             client: HttpClient::new(),
             token: Some("tok".into()),
+            api: ConditionalClient::new("github"),
+            last_from_cache: AtomicBool::new(false),
         };
         assert!(p2.has_token());
     }
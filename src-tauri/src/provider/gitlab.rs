@@ -1,14 +1,16 @@
+use super::rate_limit::{ConditionalClient, RateLimitPolicy, RateLimitStatus};
 use super::traits::*;
-use crate::download::{AssetLike, AssetPicker, LibcType};
+use crate::download::{AssetLike, AssetPicker};
 use crate::error::{CogniaError, CogniaResult};
 use crate::platform::{
-    env::{current_arch, current_platform, detect_libc, Architecture, Platform},
+    env::{current_arch, current_platform, Architecture, Platform},
     fs,
     network::HttpClient,
 };
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::path::PathBuf;
 
 const DEFAULT_GITLAB_API: &str = "https://gitlab.com/api/v4";
 const DEFAULT_GITLAB_URL: &str = "https://gitlab.com";
@@ -18,22 +20,38 @@ pub struct GitLabProvider {
     token: Option<String>,
     api_base: String,
     instance_url: String,
+    api: ConditionalClient,
 }
 
 impl GitLabProvider {
     pub fn new() -> Self {
         let token = std::env::var("GITLAB_TOKEN").ok();
+        let api = ConditionalClient::new("gitlab").with_token(token.as_deref());
         Self {
-            client: HttpClient::new(),
+            client: HttpClient::for_provider("gitlab"),
             token,
             api_base: DEFAULT_GITLAB_API.to_string(),
             instance_url: DEFAULT_GITLAB_URL.to_string(),
+            api,
         }
     }
 
+    /// Revalidate API responses with ETags kept in the metadata cache
+    pub fn with_cache_dir(mut self, cache_dir: Option<PathBuf>) -> Self {
+        self.api = self.api.with_cache_dir(cache_dir);
+        self
+    }
+
+    /// Queue (the default) or fail fast once the rate limit is exhausted
+    pub fn with_rate_limit_policy(mut self, policy: RateLimitPolicy) -> Self {
+        self.api = self.api.with_policy(policy);
+        self
+    }
+
     /// Create a provider with an explicit token (from settings)
     pub fn with_token(mut self, token: Option<String>) -> Self {
         if token.is_some() {
+            self.api = self.api.with_token(token.as_deref());
             self.token = token;
         }
         self
@@ -49,6 +67,11 @@ pub fn with_instance_url(mut self, url: Option<String>) -> Self {
         self
     }
 
+    /// The quota last seen for this instance and token
+    pub fn rate_limit_status(&self) -> Option<RateLimitStatus> {
+        super::rate_limit::rate_limit_status(&self.api.bucket(&self.api_base))
+    }
+
     fn build_request_options(&self) -> crate::platform::network::RequestOptions {
         let mut opts = crate::platform::network::RequestOptions::new()
             .with_header("Accept", "application/json");
@@ -61,10 +84,7 @@ fn build_request_options(&self) -> crate::platform::network::RequestOptions {
     pub async fn api_get<T: for<'de> Deserialize<'de>>(&self, path: &str) -> CogniaResult<T> {
         let url = format!("{}{}", self.api_base, path);
         let opts = self.build_request_options();
-        let resp = self.client.get_with_options(&url, Some(opts)).await?;
-        resp.json()
-            .await
-            .map_err(|e| CogniaError::Network(e.to_string()))
+        Ok(self.api.get_json(&self.client, &url, opts).await?.data)
     }
 
     async fn api_get_paginated<T: for<'de> Deserialize<'de>>(
@@ -77,7 +97,7 @@ async fn api_get_paginated<T: for<'de> Deserialize<'de>>(
 
         for _ in 0..max_pages {
             let opts = self.build_request_options();
-            let resp = self.client.get_with_options(&url, Some(opts)).await?;
+            let resp = self.api.send(&self.client, &url, opts, None).await?;
 
             let next_url = resp
                 .headers()
@@ -379,19 +399,7 @@ fn match_asset<'a>(
         platform: Platform,
         arch: Architecture,
     ) -> Option<&'a GitLabReleaseLink> {
-        let mut picker = AssetPicker::new(platform, arch);
-
-        if platform == Platform::Linux {
-            let libc = detect_libc();
-            let libc_type = match libc.as_str() {
-                "musl" => LibcType::Musl,
-                "glibc" => LibcType::Glibc,
-                _ => LibcType::Unknown,
-            };
-            picker = picker.with_libc(libc_type);
-        }
-
-        picker.pick_best(assets)
+        AssetPicker::for_target(platform, arch).pick_best(assets)
     }
 }
 
@@ -583,7 +591,13 @@ fn display_name(&self) -> &str {
         "GitLab Releases"
     }
     fn capabilities(&self) -> HashSet<Capability> {
-        HashSet::from([Capability::Install, Capability::Search, Capability::List])
+        HashSet::from([
+            Capability::Install,
+            Capability::Search,
+            Capability::List,
+            Capability::Info,
+            Capability::Versions,
+        ])
     }
     fn supported_platforms(&self) -> Vec<Platform> {
         vec![Platform::Linux, Platform::MacOS, Platform::Windows]
@@ -696,7 +710,7 @@ async fn install(&self, req: InstallRequest) -> CogniaResult<InstallReceipt> {
 
         // Build request options with auth header for private repos
         let opts = self.build_request_options();
-        let download_client = HttpClient::new().with_options(opts);
+        let download_client = HttpClient::for_provider("gitlab").with_options(opts);
         download_client
             .download(download_url, &dest, None::<fn(_)>)
             .await?;
@@ -777,6 +791,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                     install_path: pkg_path.clone(),
                     installed_at,
                     is_global: true,
+                    kind: None,
                 });
             }
         }
@@ -77,6 +77,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::VersionSwitch,
             Capability::MultiVersion,
             Capability::ProjectLocal,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
 
@@ -171,6 +173,17 @@ async fn get_versions(&self, _name: &str) -> CogniaResult<Vec<VersionInfo>> {
             .collect())
     }
 
+    /// `goenv install --list` can only be read whole, so diff it against the
+    /// cursor rather than handing back the complete list every sync.
+    async fn fetch_versions_incremental(
+        &self,
+        name: &str,
+        cursor: &VersionSyncCursor,
+    ) -> CogniaResult<VersionFetch> {
+        let listing = self.get_versions(name).await?;
+        Ok(VersionFetch::from_listing(listing, cursor, None, None))
+    }
+
     async fn install(&self, req: InstallRequest) -> CogniaResult<InstallReceipt> {
         let version = req
             .version
@@ -227,6 +240,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                     install_path: goenv_root.join("versions").join(version),
                     installed_at: String::new(),
                     is_global: true,
+                    kind: None,
                 })
             })
             .collect();
@@ -645,6 +659,168 @@ pub fn go_format_bytes(bytes: u64) -> String {
     }
 }
 
+/// A module directory listed by a `use` directive in go.work
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoWorkModule {
+    /// Path as written in go.work, relative to the workspace root
+    pub path: String,
+    pub dir: String,
+    /// Module path from the module's own go.mod `module` directive
+    pub module_path: Option<String>,
+    pub go_version: Option<String>,
+    pub has_go_mod: bool,
+}
+
+/// Parsed go.work workspace
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoWorkInfo {
+    pub go_work_path: String,
+    pub root: String,
+    pub go_version: Option<String>,
+    pub toolchain: Option<String>,
+    pub modules: Vec<GoWorkModule>,
+}
+
+/// Directives of a go.work file that matter for workspace discovery
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GoWorkFile {
+    pub go: Option<String>,
+    pub toolchain: Option<String>,
+    pub uses: Vec<String>,
+}
+
+/// Parse go.work `go`, `toolchain` and `use` directives (single-line and block forms)
+pub fn parse_go_work(content: &str) -> GoWorkFile {
+    let mut parsed = GoWorkFile::default();
+    let mut block: Option<String> = None;
+
+    for raw in content.lines() {
+        let line = raw.split("//").next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(directive) = block.as_deref() {
+            if line == ")" {
+                block = None;
+            } else if directive == "use" {
+                parsed.uses.push(unquote_go_work_value(line));
+            }
+            continue;
+        }
+
+        let (directive, rest) = match line.split_once(char::is_whitespace) {
+            Some((directive, rest)) => (directive, rest.trim()),
+            None => (line, ""),
+        };
+        if rest == "(" {
+            block = Some(directive.to_string());
+            continue;
+        }
+        match directive {
+            "go" if !rest.is_empty() => parsed.go = Some(rest.to_string()),
+            "toolchain" if !rest.is_empty() => parsed.toolchain = Some(rest.to_string()),
+            "use" if !rest.is_empty() => parsed.uses.push(unquote_go_work_value(rest)),
+            _ => {}
+        }
+    }
+
+    parsed
+}
+
+fn unquote_go_work_value(value: &str) -> String {
+    value
+        .trim()
+        .trim_matches(|c| c == '"' || c == '`')
+        .to_string()
+}
+
+/// Normalize a go.work `use` path for comparison: forward slashes, no `./` or trailing `/`
+pub fn normalize_go_work_use(path: &str) -> String {
+    let normalized = path.trim().replace('\\', "/");
+    let normalized = normalized.trim_end_matches('/');
+    let normalized = normalized.strip_prefix("./").unwrap_or(normalized);
+    if normalized.is_empty() {
+        ".".to_string()
+    } else {
+        normalized.to_string()
+    }
+}
+
+/// Find the go.work governing `start`, searching upwards like `go env GOWORK`
+pub fn find_go_work(start: &Path) -> Option<PathBuf> {
+    if start.is_file() {
+        if start.file_name().is_some_and(|name| name == "go.work") {
+            return Some(start.to_path_buf());
+        }
+        return start.parent().and_then(find_go_work);
+    }
+    start
+        .ancestors()
+        .map(|dir| dir.join("go.work"))
+        .find(|candidate| candidate.is_file())
+}
+
+fn go_mod_directive(content: &str, directive: &str) -> Option<String> {
+    content.lines().find_map(|raw| {
+        let line = raw.split("//").next().unwrap_or("").trim();
+        line.strip_prefix(directive)
+            .filter(|rest| rest.starts_with(char::is_whitespace))
+            .map(unquote_go_work_value)
+            .filter(|value| !value.is_empty())
+    })
+}
+
+/// Read the go.work governing `path` and describe each workspace module
+pub async fn read_go_work_info(path: &Path) -> CogniaResult<GoWorkInfo> {
+    let go_work_path = find_go_work(path).ok_or_else(|| {
+        CogniaError::Provider(format!("No go.work found at or above {}", path.display()))
+    })?;
+    let root = go_work_path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    let content = crate::platform::fs::read_file_string(&go_work_path).await?;
+    let parsed = parse_go_work(&content);
+
+    let mut modules = Vec::with_capacity(parsed.uses.len());
+    for use_path in &parsed.uses {
+        let dir = root.join(use_path);
+        let go_mod = crate::platform::fs::read_file_string(dir.join("go.mod"))
+            .await
+            .ok();
+        modules.push(GoWorkModule {
+            path: normalize_go_work_use(use_path),
+            dir: dir.display().to_string(),
+            module_path: go_mod
+                .as_deref()
+                .and_then(|content| go_mod_directive(content, "module")),
+            go_version: go_mod
+                .as_deref()
+                .and_then(|content| go_mod_directive(content, "go")),
+            has_go_mod: go_mod.is_some(),
+        });
+    }
+
+    Ok(GoWorkInfo {
+        go_work_path: go_work_path.display().to_string(),
+        root: root.display().to_string(),
+        go_version: parsed.go,
+        toolchain: parsed.toolchain,
+        modules,
+    })
+}
+
+/// Resolve `module` (a go.work `use` path or module path) to its directory within the workspace
+pub fn resolve_go_work_module<'a>(info: &'a GoWorkInfo, module: &str) -> Option<&'a GoWorkModule> {
+    let wanted = normalize_go_work_use(module);
+    info.modules.iter().find(|candidate| {
+        candidate.path == wanted || candidate.module_path.as_deref() == Some(module.trim())
+    })
+}
+
 #[async_trait]
 impl Provider for GoModProvider {
     fn id(&self) -> &str {
@@ -661,6 +837,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::Uninstall,
             Capability::Search,
             Capability::List,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
 
@@ -1002,6 +1180,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                             install_path: path,
                             installed_at,
                             is_global: true,
+                            kind: None,
                         });
                     }
                 }
@@ -1112,7 +1291,7 @@ fn test_goenv_provider_capabilities() {
         assert!(caps.contains(&Capability::VersionSwitch));
         assert!(caps.contains(&Capability::MultiVersion));
         assert!(caps.contains(&Capability::ProjectLocal));
-        assert_eq!(caps.len(), 7);
+        assert_eq!(caps.len(), 9);
     }
 
     #[test]
@@ -1176,7 +1355,7 @@ fn test_go_mod_provider_capabilities() {
         assert!(caps.contains(&Capability::Uninstall));
         assert!(caps.contains(&Capability::Search));
         assert!(caps.contains(&Capability::List));
-        assert_eq!(caps.len(), 4);
+        assert_eq!(caps.len(), 6);
         // Should NOT have version management capabilities
         assert!(!caps.contains(&Capability::VersionSwitch));
         assert!(!caps.contains(&Capability::MultiVersion));
@@ -1476,6 +1655,82 @@ fn test_go_cache_info_zero_values() {
         assert_eq!(deserialized.mod_cache_size, 0);
     }
 
+    // ── go.work tests ─────────────────────────────────────
+
+    #[test]
+    fn test_parse_go_work_single_line_and_block_uses() {
+        let parsed = parse_go_work(
+            r#"
+go 1.22.0
+toolchain go1.22.3 // pinned for CI
+
+use ./api
+use (
+    ./services/auth
+    "./services/billing"
+    // ./legacy
+)
+
+replace example.com/old => ./vendor/old
+"#,
+        );
+        assert_eq!(parsed.go.as_deref(), Some("1.22.0"));
+        assert_eq!(parsed.toolchain.as_deref(), Some("go1.22.3"));
+        assert_eq!(
+            parsed.uses,
+            vec!["./api", "./services/auth", "./services/billing"]
+        );
+    }
+
+    #[test]
+    fn test_normalize_go_work_use() {
+        assert_eq!(normalize_go_work_use("./api/"), "api");
+        assert_eq!(normalize_go_work_use(".\\services\\auth"), "services/auth");
+        assert_eq!(normalize_go_work_use("."), ".");
+        assert_eq!(normalize_go_work_use("./"), ".");
+    }
+
+    #[tokio::test]
+    async fn test_read_go_work_info_without_root_go_mod() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::write(
+            root.join("go.work"),
+            "go 1.22.0\n\nuse (\n\t./api\n\t./missing\n)\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("api").join("internal")).unwrap();
+        std::fs::write(
+            root.join("api").join("go.mod"),
+            "module example.com/api\n\ngo 1.21\n",
+        )
+        .unwrap();
+
+        let info = read_go_work_info(&root.join("api").join("internal"))
+            .await
+            .unwrap();
+        assert_eq!(info.go_version.as_deref(), Some("1.22.0"));
+        assert!(info.toolchain.is_none());
+        assert_eq!(info.modules.len(), 2);
+        assert_eq!(info.modules[0].path, "api");
+        assert_eq!(
+            info.modules[0].module_path.as_deref(),
+            Some("example.com/api")
+        );
+        assert_eq!(info.modules[0].go_version.as_deref(), Some("1.21"));
+        assert!(!info.modules[1].has_go_mod);
+
+        assert_eq!(
+            resolve_go_work_module(&info, "./api/").map(|m| m.path.as_str()),
+            Some("api")
+        );
+        assert_eq!(
+            resolve_go_work_module(&info, "example.com/api").map(|m| m.path.as_str()),
+            Some("api")
+        );
+        assert!(resolve_go_work_module(&info, "other").is_none());
+    }
+
     // ── Helper function tests ─────────────────────────────
 
     #[test]
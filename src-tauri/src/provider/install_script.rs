@@ -0,0 +1,134 @@
+//! Installer scripts run by provider self-installs (SDKMAN, FVM).
+//!
+//! Vendor install scripts change with every release, so they can't be
+//! pinned to a hash here. Instead the caller previews the script, shows its
+//! SHA-256 to the user, and passes that hash back to [`run`], which refuses
+//! to execute anything else. The script runs from a private temp file, never
+//! from a predictable path another user could pre-create.
+
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::process::{self, ProcessOptions, ProcessOutput};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::time::Duration;
+
+/// Vendor install scripts are a few dozen KB; anything larger is not one
+const MAX_SCRIPT_BYTES: usize = 1024 * 1024;
+
+/// A downloaded installer script, for the user to review before running it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallScript {
+    pub url: String,
+    pub sha256: String,
+    pub content: String,
+}
+
+/// Check that `bytes` look like a shell script and hash them
+pub fn inspect(url: &str, bytes: &[u8]) -> CogniaResult<InstallScript> {
+    if bytes.len() > MAX_SCRIPT_BYTES {
+        return Err(CogniaError::Installation(format!(
+            "Install script from {} is larger than {} bytes",
+            url, MAX_SCRIPT_BYTES
+        )));
+    }
+    let content = std::str::from_utf8(bytes).map_err(|_| {
+        CogniaError::Installation(format!("Install script from {} is not text", url))
+    })?;
+    let shebang = content.lines().next().unwrap_or_default();
+    if !shebang.starts_with("#!") || !shebang.contains("sh") {
+        return Err(CogniaError::Installation(format!(
+            "Install script from {} is not a shell script",
+            url
+        )));
+    }
+
+    Ok(InstallScript {
+        url: url.to_string(),
+        sha256: hex::encode(Sha256::digest(bytes)),
+        content: content.to_string(),
+    })
+}
+
+/// Download the script at `url` through the proxy settings of `provider_id`
+pub async fn fetch(provider_id: &str, url: &str) -> CogniaResult<InstallScript> {
+    let client = crate::platform::proxy::get_client_for_provider(provider_id);
+    let bytes = client
+        .get(url)
+        .timeout(Duration::from_secs(60))
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| CogniaError::Network(format!("Failed to download {}: {}", url, e)))?
+        .bytes()
+        .await
+        .map_err(|e| CogniaError::Network(format!("Failed to download {}: {}", url, e)))?;
+    inspect(url, &bytes)
+}
+
+/// Download the script at `url` and run it with `interpreter`, but only if
+/// it still hashes to `expected_sha256`, the hash the user reviewed
+pub async fn run(
+    provider_id: &str,
+    url: &str,
+    expected_sha256: &str,
+    interpreter: &str,
+    args: &[&str],
+    timeout: Duration,
+) -> CogniaResult<ProcessOutput> {
+    let script = fetch(provider_id, url).await?;
+    let expected = expected_sha256.trim().to_ascii_lowercase();
+    if script.sha256 != expected {
+        return Err(CogniaError::ChecksumMismatch {
+            expected,
+            actual: script.sha256,
+        });
+    }
+
+    // Randomly named and created 0600, so nobody can swap the file between
+    // the write and the run
+    let prefix = format!("{}-install-", provider_id);
+    let file = tokio::task::spawn_blocking(move || -> std::io::Result<_> {
+        let mut file = tempfile::Builder::new()
+            .prefix(&prefix)
+            .suffix(".sh")
+            .tempfile()?;
+        file.write_all(script.content.as_bytes())?;
+        file.flush()?;
+        Ok(file)
+    })
+    .await
+    .map_err(|e| CogniaError::Internal(e.to_string()))??;
+
+    let script_arg = file.path().to_string_lossy().into_owned();
+    let mut full_args = vec![script_arg.as_str()];
+    full_args.extend_from_slice(args);
+    let opts = ProcessOptions::new().with_timeout(timeout);
+    let output = process::execute(interpreter, &full_args, Some(opts)).await;
+    drop(file);
+    Ok(output?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inspect_hashes_shell_scripts() {
+        let script = inspect("https://example.com/install.sh", b"#!/bin/bash\necho hi\n").unwrap();
+        assert_eq!(
+            script.sha256,
+            hex::encode(Sha256::digest(b"#!/bin/bash\necho hi\n"))
+        );
+        assert_eq!(script.content, "#!/bin/bash\necho hi\n");
+    }
+
+    #[test]
+    fn test_inspect_rejects_non_scripts() {
+        let url = "https://example.com/install.sh";
+        assert!(inspect(url, b"<!DOCTYPE html><html></html>").is_err());
+        assert!(inspect(url, &[0xff, 0xfe, 0x00]).is_err());
+        assert!(inspect(url, &vec![b'#'; MAX_SCRIPT_BYTES + 1]).is_err());
+    }
+}
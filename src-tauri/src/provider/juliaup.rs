@@ -0,0 +1,667 @@
+use super::traits::*;
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::{
+    env::{EnvModifications, Platform},
+    process::{self, ProcessOptions},
+};
+use async_trait::async_trait;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const JULIAUP_INSTALL_SCRIPT_URL: &str = "https://install.julialang.org";
+/// Microsoft Store id juliaup is published under on Windows
+const JULIAUP_STORE_ID: &str = "9NJNWW8PVKMN";
+
+/// A channel installed through juliaup, from `juliaup status`
+#[derive(Debug, Clone, PartialEq)]
+pub struct JuliaupChannel {
+    /// Channel name as passed to `juliaup add` (`release`, `lts`, `1.10`, `1.10.4`)
+    pub channel: String,
+    /// Full version juliaup reports, e.g. `1.10.4+0.x64.linux.gnu`
+    pub full_version: String,
+    pub is_default: bool,
+    /// Newer version the channel can be updated to
+    pub update: Option<String>,
+}
+
+impl JuliaupChannel {
+    pub fn version(&self) -> &str {
+        plain_julia_version(&self.full_version)
+    }
+}
+
+/// juliaup - the official Julia version multiplexer
+///
+/// Julia versions are installed as channels (`release`, `lts`, explicit
+/// versions) under juliaup's depot; `juliaup default` picks the global one.
+pub struct JuliaupProvider {
+    juliaup_dir: Option<PathBuf>,
+}
+
+impl JuliaupProvider {
+    pub fn new() -> Self {
+        Self {
+            juliaup_dir: Self::detect_juliaup_dir(),
+        }
+    }
+
+    fn detect_juliaup_dir() -> Option<PathBuf> {
+        // juliaup keeps its installs under the first depot
+        if let Ok(depot) = std::env::var("JULIAUP_DEPOT_PATH") {
+            return Some(PathBuf::from(depot).join("juliaup"));
+        }
+        if let Ok(depots) = std::env::var("JULIA_DEPOT_PATH") {
+            #[cfg(windows)]
+            let sep = ';';
+            #[cfg(not(windows))]
+            let sep = ':';
+            if let Some(first) = depots.split(sep).find(|d| !d.is_empty()) {
+                return Some(PathBuf::from(first).join("juliaup"));
+            }
+        }
+        dirs::home_dir().map(|h| h.join(".julia").join("juliaup"))
+    }
+
+    fn juliaup_dir(&self) -> CogniaResult<PathBuf> {
+        self.juliaup_dir
+            .clone()
+            .ok_or_else(|| CogniaError::Provider("juliaup directory not found".into()))
+    }
+
+    async fn run_juliaup(&self, args: &[&str]) -> CogniaResult<String> {
+        self.run_juliaup_with_timeout(args, Duration::from_secs(120))
+            .await
+    }
+
+    async fn run_juliaup_with_timeout(
+        &self,
+        args: &[&str],
+        timeout: Duration,
+    ) -> CogniaResult<String> {
+        let opts = ProcessOptions::new().with_timeout(timeout);
+        let output = process::execute("juliaup", args, Some(opts)).await?;
+        if output.success {
+            Ok(output.stdout)
+        } else {
+            Err(CogniaError::Provider(output.stderr))
+        }
+    }
+
+    /// Installed channels from `juliaup status`
+    pub async fn list_channels(&self) -> CogniaResult<Vec<JuliaupChannel>> {
+        let output = self.run_juliaup(&["status"]).await?;
+        Ok(parse_status(&output))
+    }
+
+    /// Installed channel providing `version`, matched by channel name first
+    async fn channel_for(&self, version: &str) -> CogniaResult<JuliaupChannel> {
+        let channels = self.list_channels().await?;
+        channels
+            .iter()
+            .find(|c| c.channel == version)
+            .or_else(|| channels.iter().find(|c| c.version() == version))
+            .cloned()
+            .ok_or_else(|| CogniaError::VersionNotInstalled(format!("julia {}", version)))
+    }
+
+    fn install_dir(&self, full_version: &str) -> CogniaResult<PathBuf> {
+        Ok(self.juliaup_dir()?.join(format!("julia-{}", full_version)))
+    }
+
+    /// Install juliaup itself: the official install script on macOS/Linux,
+    /// the Microsoft Store package through winget on Windows
+    pub async fn self_install(&self) -> CogniaResult<String> {
+        let opts = || ProcessOptions::new().with_timeout(Duration::from_secs(600));
+
+        if cfg!(windows) {
+            let output = process::execute(
+                "winget",
+                &[
+                    "install",
+                    "--id",
+                    JULIAUP_STORE_ID,
+                    "-e",
+                    "--source",
+                    "msstore",
+                    "--accept-package-agreements",
+                    "--accept-source-agreements",
+                ],
+                Some(opts()),
+            )
+            .await?;
+            return if output.success {
+                Ok(output.stdout)
+            } else {
+                Err(CogniaError::Installation(output.stderr))
+            };
+        }
+
+        let client = crate::platform::proxy::get_client_for_provider("juliaup");
+        let response = client
+            .get(JULIAUP_INSTALL_SCRIPT_URL)
+            .timeout(Duration::from_secs(60))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| {
+                CogniaError::Network(format!("Failed to download juliaup installer: {}", e))
+            })?;
+        let script = response.bytes().await.map_err(|e| {
+            CogniaError::Network(format!("Failed to download juliaup installer: {}", e))
+        })?;
+
+        let script_path =
+            std::env::temp_dir().join(format!("juliaup-install-{}.sh", std::process::id()));
+        crate::platform::fs::write_file(&script_path, &script).await?;
+        let script_arg = script_path.to_string_lossy().into_owned();
+        let result = process::execute("sh", &[script_arg.as_str(), "--yes"], Some(opts())).await;
+        let _ = crate::platform::fs::remove_file(&script_path).await;
+
+        let output = result?;
+        if output.success {
+            Ok(output.stdout)
+        } else {
+            Err(CogniaError::Installation(output.stderr))
+        }
+    }
+}
+
+impl Default for JuliaupProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `1.10.4+0.x64.linux.gnu` -> `1.10.4`
+pub fn plain_julia_version(full: &str) -> &str {
+    full.split('+').next().unwrap_or(full).trim()
+}
+
+fn is_table_decoration(line: &str) -> bool {
+    line.chars().all(|c| c == '-' || c.is_whitespace())
+}
+
+/// Parse `juliaup status`:
+///
+/// ```text
+///  Default  Channel  Version                 Update
+/// ---------------------------------------------------
+///        *  release  1.10.4+0.x64.linux.gnu
+///           lts      1.6.7+0.x64.linux.gnu   Update to 1.6.8+0.x64.linux.gnu
+/// ```
+pub fn parse_status(output: &str) -> Vec<JuliaupChannel> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !is_table_decoration(line))
+        .filter(|line| !line.starts_with("Default"))
+        .filter_map(|line| {
+            let (is_default, rest) = match line.strip_prefix('*') {
+                Some(rest) => (true, rest.trim_start()),
+                None => (false, line),
+            };
+            let mut tokens = rest.split_whitespace();
+            let channel = tokens.next()?.to_string();
+            let full_version = tokens.next()?.to_string();
+            let update = tokens
+                .last()
+                .filter(|v| v.chars().next().is_some_and(|c| c.is_ascii_digit()))
+                .map(|v| plain_julia_version(v).to_string());
+            Some(JuliaupChannel {
+                channel,
+                full_version,
+                is_default,
+                update,
+            })
+        })
+        .collect()
+}
+
+/// Parse `juliaup list` into `(channel id, version)` pairs
+pub fn parse_list(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !is_table_decoration(line))
+        .filter(|line| !line.starts_with("Id"))
+        .filter_map(|line| {
+            let mut tokens = line.split_whitespace();
+            let id = tokens.next()?;
+            let version = tokens.next()?;
+            Some((id.to_string(), plain_julia_version(version).to_string()))
+        })
+        .collect()
+}
+
+/// Whether `version` satisfies a `[compat]` entry such as `1.6`, `^1.6, 1.9`,
+/// `~1.10.2`, `>= 1.9` or `1.6 - 1.9`. Comma-separated specs are alternatives.
+pub fn compat_matches(spec: &str, version: &str) -> bool {
+    let Ok(version) = semver::Version::parse(version.trim().trim_start_matches('v')) else {
+        return false;
+    };
+    spec.split(',').map(str::trim).any(|part| {
+        let req = if let Some((low, high)) = part.split_once(" - ") {
+            format!(">={}, <={}", low.trim(), high.trim())
+        } else if part.starts_with(|c: char| c.is_ascii_digit()) {
+            // A bare version is a caret specifier in Julia
+            format!("^{}", part)
+        } else {
+            part.replace('≥', ">=").replace('≤', "<=")
+        };
+        semver::VersionReq::parse(&req)
+            .map(|req| req.matches(&version))
+            .unwrap_or(false)
+    })
+}
+
+#[async_trait]
+impl Provider for JuliaupProvider {
+    fn id(&self) -> &str {
+        "juliaup"
+    }
+
+    fn display_name(&self) -> &str {
+        "juliaup (Julia Version Manager)"
+    }
+
+    fn capabilities(&self) -> HashSet<Capability> {
+        HashSet::from([
+            Capability::Install,
+            Capability::Uninstall,
+            Capability::Search,
+            Capability::List,
+            Capability::Update,
+            Capability::VersionSwitch,
+            Capability::MultiVersion,
+            Capability::ProjectLocal,
+            Capability::Info,
+            Capability::Versions,
+        ])
+    }
+
+    fn supported_platforms(&self) -> Vec<Platform> {
+        vec![Platform::Windows, Platform::MacOS, Platform::Linux]
+    }
+
+    fn priority(&self) -> i32 {
+        85
+    }
+
+    async fn is_available(&self) -> bool {
+        if process::which("juliaup").await.is_none() {
+            return false;
+        }
+        match process::execute("juliaup", &["--version"], None).await {
+            Ok(output) => output.success,
+            Err(_) => false,
+        }
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        _options: SearchOptions,
+    ) -> CogniaResult<Vec<PackageSummary>> {
+        let versions = self.get_versions("julia").await?;
+        Ok(versions
+            .into_iter()
+            .filter(|v| query.is_empty() || v.version.contains(query))
+            .take(20)
+            .map(|v| PackageSummary {
+                name: format!("julia@{}", v.version),
+                description: Some("Julia programming language".into()),
+                latest_version: Some(v.version),
+                provider: self.id().to_string(),
+            })
+            .collect())
+    }
+
+    async fn get_package_info(&self, name: &str) -> CogniaResult<PackageInfo> {
+        let version = name.strip_prefix("julia@").unwrap_or(name);
+
+        Ok(PackageInfo {
+            name: name.into(),
+            display_name: Some(format!("Julia {}", version)),
+            description: Some("Julia programming language".into()),
+            homepage: Some("https://julialang.org".into()),
+            license: Some("MIT".into()),
+            repository: Some("https://github.com/JuliaLang/julia".into()),
+            versions: vec![VersionInfo {
+                version: version.to_string(),
+                release_date: None,
+                deprecated: false,
+                yanked: false,
+            }],
+            provider: self.id().into(),
+        })
+    }
+
+    async fn get_versions(&self, _name: &str) -> CogniaResult<Vec<VersionInfo>> {
+        let output = self.run_juliaup(&["list"]).await?;
+        // Only explicit versions; channels like `release` are aliases of them
+        let mut versions: Vec<semver::Version> = parse_list(&output)
+            .into_iter()
+            .filter_map(|(id, _)| semver::Version::parse(&id).ok())
+            .collect();
+        versions.sort_by(|a, b| b.cmp(a));
+        versions.dedup();
+
+        Ok(versions
+            .into_iter()
+            .map(|version| VersionInfo {
+                version: version.to_string(),
+                release_date: None,
+                deprecated: false,
+                yanked: false,
+            })
+            .collect())
+    }
+
+    async fn install(&self, req: InstallRequest) -> CogniaResult<InstallReceipt> {
+        let version = req.version.as_deref().unwrap_or("release");
+
+        self.run_juliaup_with_timeout(&["add", version], Duration::from_secs(900))
+            .await?;
+
+        let channel = self.channel_for(version).await?;
+        Ok(InstallReceipt {
+            name: "julia".to_string(),
+            version: channel.version().to_string(),
+            provider: self.id().to_string(),
+            install_path: self.install_dir(&channel.full_version)?,
+            files: vec![],
+            installed_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    async fn uninstall(&self, req: UninstallRequest) -> CogniaResult<()> {
+        let version = req
+            .version
+            .ok_or_else(|| CogniaError::Provider("Version required for uninstall".into()))?;
+
+        let channel = self.channel_for(&version).await?;
+        self.run_juliaup(&["remove", &channel.channel]).await?;
+        Ok(())
+    }
+
+    async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<InstalledPackage>> {
+        Ok(self
+            .list_installed_versions()
+            .await?
+            .into_iter()
+            .map(|v| InstalledPackage {
+                name: format!("julia@{}", v.version),
+                version: v.version,
+                provider: self.id().into(),
+                install_path: v.install_path,
+                installed_at: String::new(),
+                is_global: true,
+                kind: None,
+            })
+            .filter(|p| match filter.name_filter {
+                Some(ref name_filter) => p.name.contains(name_filter),
+                None => true,
+            })
+            .collect())
+    }
+
+    async fn check_updates(&self, _packages: &[String]) -> CogniaResult<Vec<UpdateInfo>> {
+        let channels = self.list_channels().await.unwrap_or_default();
+        Ok(channels
+            .iter()
+            .filter_map(|c| {
+                let latest = c.update.clone()?;
+                Some(UpdateInfo {
+                    name: format!("julia@{}", c.channel),
+                    current_version: c.version().to_string(),
+                    latest_version: latest,
+                    provider: self.id().into(),
+                })
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl EnvironmentProvider for JuliaupProvider {
+    async fn list_installed_versions(&self) -> CogniaResult<Vec<InstalledVersion>> {
+        let channels = self.list_channels().await?;
+        let mut versions: Vec<InstalledVersion> = Vec::new();
+        for channel in &channels {
+            let version = channel.version().to_string();
+            // Several channels (`release`, `1.10`) can point at one install
+            if let Some(existing) = versions.iter_mut().find(|v| v.version == version) {
+                existing.is_current |= channel.is_default;
+                continue;
+            }
+            versions.push(InstalledVersion {
+                install_path: self.install_dir(&channel.full_version)?,
+                version,
+                size: None,
+                installed_at: None,
+                is_current: channel.is_default,
+            });
+        }
+        Ok(versions)
+    }
+
+    async fn get_current_version(&self) -> CogniaResult<Option<String>> {
+        Ok(self
+            .list_channels()
+            .await?
+            .into_iter()
+            .find(|c| c.is_default)
+            .map(|c| c.version().to_string()))
+    }
+
+    async fn set_global_version(&self, version: &str) -> CogniaResult<()> {
+        let channel = self.channel_for(version).await?;
+        self.run_juliaup(&["default", &channel.channel]).await?;
+        Ok(())
+    }
+
+    async fn set_local_version(&self, project_path: &Path, version: &str) -> CogniaResult<()> {
+        let version_file = project_path.join(self.version_file_name());
+        crate::platform::fs::write_file_string(&version_file, version).await?;
+        Ok(())
+    }
+
+    async fn detect_version(&self, start_path: &Path) -> CogniaResult<Option<VersionDetection>> {
+        let mut current = start_path.to_path_buf();
+        loop {
+            let version_file = current.join(".julia-version");
+            if version_file.exists() {
+                if let Ok(content) = crate::platform::fs::read_file_string(&version_file).await {
+                    let version = content.trim().to_string();
+                    if !version.is_empty() {
+                        return Ok(Some(VersionDetection {
+                            version,
+                            source: VersionSource::LocalFile,
+                            source_path: Some(version_file),
+                        }));
+                    }
+                }
+            }
+
+            let tool_versions = current.join(".tool-versions");
+            if tool_versions.exists() {
+                if let Ok(content) = crate::platform::fs::read_file_string(&tool_versions).await {
+                    for line in content.lines() {
+                        if let Some(version) = line.trim().strip_prefix("julia ") {
+                            let version = version.trim();
+                            if !version.is_empty() {
+                                return Ok(Some(VersionDetection {
+                                    version: version.to_string(),
+                                    source: VersionSource::LocalFile,
+                                    source_path: Some(tool_versions),
+                                }));
+                            }
+                        }
+                    }
+                }
+            }
+
+            if !current.pop() {
+                break;
+            }
+        }
+
+        if let Some(version) = self.get_current_version().await? {
+            return Ok(Some(VersionDetection {
+                version,
+                source: VersionSource::SystemDefault,
+                source_path: None,
+            }));
+        }
+
+        Ok(None)
+    }
+
+    fn get_env_modifications(&self, version: &str) -> CogniaResult<EnvModifications> {
+        let juliaup_dir = self.juliaup_dir()?;
+        // Install dirs carry the build suffix (`julia-1.10.4+0.x64.linux.gnu`)
+        let prefix = format!("julia-{}+", version);
+        let install_dir = std::fs::read_dir(&juliaup_dir)
+            .ok()
+            .and_then(|entries| {
+                entries.flatten().map(|e| e.path()).find(|path| {
+                    path.file_name()
+                        .and_then(|n| n.to_str())
+                        .is_some_and(|n| n.starts_with(&prefix))
+                })
+            })
+            .unwrap_or_else(|| juliaup_dir.join(format!("julia-{}", version)));
+        Ok(EnvModifications::new().prepend_path(install_dir.join("bin")))
+    }
+
+    fn version_file_name(&self) -> &str {
+        ".julia-version"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[async_trait]
+impl SystemPackageProvider for JuliaupProvider {
+    async fn check_system_requirements(&self) -> CogniaResult<bool> {
+        Ok(self.is_available().await)
+    }
+
+    fn requires_elevation(&self, _operation: &str) -> bool {
+        false
+    }
+
+    async fn get_version(&self) -> CogniaResult<String> {
+        let output = self.run_juliaup(&["--version"]).await?;
+        Ok(output
+            .trim()
+            .trim_start_matches("Juliaup")
+            .trim()
+            .trim_start_matches('v')
+            .to_string())
+    }
+
+    async fn get_executable_path(&self) -> CogniaResult<PathBuf> {
+        process::which("juliaup")
+            .await
+            .map(PathBuf::from)
+            .ok_or_else(|| CogniaError::Provider("juliaup not found in PATH".into()))
+    }
+
+    fn get_install_instructions(&self) -> Option<String> {
+        if cfg!(windows) {
+            Some(format!(
+                "winget install --id {} -e --source msstore",
+                JULIAUP_STORE_ID
+            ))
+        } else {
+            Some(format!("curl -fsSL {} | sh", JULIAUP_INSTALL_SCRIPT_URL))
+        }
+    }
+
+    async fn is_package_installed(&self, name: &str) -> CogniaResult<bool> {
+        let version = name.strip_prefix("julia@").unwrap_or(name);
+        let versions = self.list_installed_versions().await?;
+        Ok(versions.iter().any(|v| v.version == version))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_status() {
+        let output = r#" Default  Channel  Version                 Update
+---------------------------------------------------------------
+       *  release  1.10.4+0.x64.linux.gnu
+          lts      1.6.7+0.x64.linux.gnu   Update to 1.6.8+0.x64.linux.gnu
+          1.9      1.9.4+0.x64.linux.gnu
+"#;
+        let channels = parse_status(output);
+        assert_eq!(channels.len(), 3);
+        assert_eq!(channels[0].channel, "release");
+        assert!(channels[0].is_default);
+        assert_eq!(channels[0].version(), "1.10.4");
+        assert_eq!(channels[0].update, None);
+        assert_eq!(channels[1].update.as_deref(), Some("1.6.8"));
+        assert!(!channels[2].is_default);
+        assert_eq!(channels[2].full_version, "1.9.4+0.x64.linux.gnu");
+    }
+
+    #[test]
+    fn test_parse_list() {
+        let output = r#" Id                   Version
+------------------------------------------------
+ release              1.10.4+0.x64.linux.gnu
+ 1.10.4               1.10.4+0.x64.linux.gnu
+ 1.11.0-rc1           1.11.0-rc1+0.x64.linux.gnu
+"#;
+        let list = parse_list(output);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list[0], ("release".to_string(), "1.10.4".to_string()));
+        assert_eq!(list[2].1, "1.11.0-rc1");
+    }
+
+    #[test]
+    fn test_compat_matches() {
+        assert!(compat_matches("1.6", "1.10.4"));
+        assert!(!compat_matches("1.6", "2.0.0"));
+        assert!(compat_matches("0.7", "0.7.3"));
+        assert!(!compat_matches("0.7", "0.8.0"));
+        assert!(compat_matches("1.6, 1.9", "1.9.1"));
+        assert!(compat_matches("~1.10.2", "1.10.4"));
+        assert!(!compat_matches("~1.10.2", "1.11.0"));
+        assert!(compat_matches(">= 1.9", "1.11.1"));
+        assert!(compat_matches("=1.9.4", "1.9.4"));
+        assert!(compat_matches("1.6 - 1.9", "1.9.4"));
+        assert!(!compat_matches("1.6 - 1.9", "1.10.0"));
+        assert!(!compat_matches("1.6", "release"));
+    }
+
+    #[test]
+    fn test_provider_metadata() {
+        let provider = JuliaupProvider::new();
+        assert_eq!(provider.id(), "juliaup");
+        assert_eq!(provider.version_file_name(), ".julia-version");
+        assert!(provider.capabilities().contains(&Capability::VersionSwitch));
+        assert!(!provider.requires_elevation("install"));
+    }
+
+    #[test]
+    fn test_get_env_modifications_finds_suffixed_install() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("julia-1.10.4+0.x64.linux.gnu")).unwrap();
+        let provider = JuliaupProvider {
+            juliaup_dir: Some(dir.path().to_path_buf()),
+        };
+        let mods = provider.get_env_modifications("1.10.4").unwrap();
+        assert_eq!(
+            mods.path_prepend,
+            vec![dir.path().join("julia-1.10.4+0.x64.linux.gnu").join("bin")]
+        );
+    }
+}
@@ -271,6 +271,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::List,
             Capability::Update,
             Capability::Upgrade,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
 
@@ -526,6 +528,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                 },
                 installed_at: String::new(),
                 is_global: true,
+                kind: None,
             })
             .collect())
     }
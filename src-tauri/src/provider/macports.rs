@@ -93,6 +93,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::Update,
             Capability::Upgrade,
             Capability::UpdateIndex,
+            Capability::Info,
+            Capability::RequiresElevation,
         ])
     }
 
@@ -291,6 +293,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                     install_path: PathBuf::from("/opt/local"),
                     installed_at: String::new(),
                     is_global: true,
+                    kind: None,
                 })
             })
             .collect())
@@ -411,7 +414,7 @@ fn test_capabilities() {
         assert!(caps.contains(&Capability::Update));
         assert!(caps.contains(&Capability::Upgrade));
         assert!(caps.contains(&Capability::UpdateIndex));
-        assert_eq!(caps.len(), 7);
+        assert_eq!(caps.len(), 9);
     }
 
     #[test]
@@ -504,6 +507,7 @@ fn test_parse_port_installed_output() {
                     install_path: PathBuf::from("/opt/local"),
                     installed_at: String::new(),
                     is_global: true,
+                    kind: None,
                 })
             })
             .collect();
@@ -107,6 +107,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::VersionSwitch,
             Capability::MultiVersion,
             Capability::ProjectLocal,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
 
@@ -268,6 +270,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                     install_path,
                     installed_at: String::new(),
                     is_global: true,
+                    kind: None,
                 })
                 .collect());
         }
@@ -302,6 +305,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                     install_path: mise_data.join("installs").join(&name).join(&version),
                     installed_at: String::new(),
                     is_global: true,
+                    kind: None,
                 })
             })
             .collect())
@@ -12,6 +12,8 @@
 pub mod conan;
 pub mod conda;
 pub mod cpp_compiler;
+pub mod cuda;
+pub mod custom_env;
 pub mod deno;
 pub mod dnf;
 pub mod fvm;
@@ -25,6 +27,8 @@
 pub mod github;
 pub mod gitlab;
 pub mod goenv;
+pub mod install_script;
+pub mod juliaup;
 pub mod luarocks;
 pub mod macports;
 pub mod mise;
@@ -38,17 +42,23 @@
 pub mod phpbrew;
 pub mod pip;
 pub mod pipx;
+pub mod pixi;
 pub mod pnpm;
 pub mod podman;
 pub mod poetry;
 pub mod psgallery;
 pub mod pub_dev;
 pub mod pyenv;
+pub mod rate_limit;
 pub mod rbenv;
 pub mod registry;
+#[cfg(test)]
+mod replay_tests;
+pub mod rig;
 pub mod rustup;
 pub mod scoop;
 pub mod sdkman;
+pub mod selftest;
 pub mod snap;
 pub mod support;
 pub mod system;
@@ -58,6 +68,7 @@
 pub mod volta;
 pub mod winget;
 pub mod wsl;
+pub mod wsl_config;
 pub mod xmake;
 pub mod yarn;
 pub mod zig;
@@ -633,7 +633,7 @@ fn display_name(&self) -> &str {
     }
 
     fn capabilities(&self) -> HashSet<Capability> {
-        HashSet::from([Capability::List])
+        HashSet::from([Capability::List, Capability::Info])
     }
 
     fn supported_platforms(&self) -> Vec<Platform> {
@@ -787,6 +787,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                 install_path: PathBuf::from(&validated.instance.installation_path),
                 installed_at: String::new(),
                 is_global: true,
+                kind: None,
             });
         }
 
@@ -1138,10 +1139,11 @@ fn test_capabilities() {
         let p = MsvcProvider { vswhere_path: None };
         let caps = p.capabilities();
         assert!(caps.contains(&Capability::List));
-        assert_eq!(caps.len(), 1);
+        assert_eq!(caps.len(), 2);
         // Should NOT have Search, Install, Uninstall
         assert!(!caps.contains(&Capability::Install));
         assert!(!caps.contains(&Capability::Search));
+        assert!(!caps.contains(&Capability::Versions));
     }
 
     #[test]
@@ -100,6 +100,7 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::Search,
             Capability::List,
             Capability::Update,
+            Capability::Info,
         ])
     }
 
@@ -362,6 +363,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                                     install_path: PathBuf::from(store_path),
                                     installed_at: String::new(),
                                     is_global: true,
+                                    kind: None,
                                 })
                             })
                             .collect());
@@ -413,6 +415,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                     install_path,
                     installed_at: String::new(),
                     is_global: true,
+                    kind: None,
                 })
             })
             .collect())
@@ -1,6 +1,7 @@
 use crate::error::{CogniaError, CogniaResult};
 use crate::platform::process;
-use crate::provider::InstallReceipt;
+use crate::provider::{InstallReceipt, VersionFetch, VersionInfo, VersionSyncCursor};
+use reqwest::Client;
 use std::collections::HashSet;
 use std::path::PathBuf;
 
@@ -397,6 +398,84 @@ pub fn parse_node_list_json_output(output: &str) -> Vec<(String, String)> {
     entries
 }
 
+/// Official Node.js distribution, used unless a version manager names a mirror
+pub const NODE_DIST_URL: &str = "https://nodejs.org/dist";
+
+/// Releases listed in a Node.js `dist/index.json`, newest first, dated by
+/// their release day. Versions keep their `v` prefix, as fnm and nvm list them.
+pub fn parse_node_dist_index(index: &serde_json::Value) -> Vec<VersionInfo> {
+    index
+        .as_array()
+        .map(|releases| {
+            releases
+                .iter()
+                .filter_map(|release| {
+                    let version = release["version"].as_str()?;
+                    Some(VersionInfo {
+                        version: version.to_string(),
+                        release_date: release["date"].as_str().map(String::from),
+                        deprecated: false,
+                        yanked: false,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Sync Node.js versions from `{dist_url}/index.json`. The request is
+/// conditional on the cursor's validators, and the index is diffed against
+/// the versions the cursor already knows.
+pub async fn fetch_node_dist_versions(
+    client: &Client,
+    dist_url: &str,
+    cursor: &VersionSyncCursor,
+) -> CogniaResult<VersionFetch> {
+    let url = format!("{}/index.json", dist_url.trim_end_matches('/'));
+    let mut request = client.get(&url);
+    if let Some(etag) = &cursor.etag {
+        request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = &cursor.last_modified {
+        request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| CogniaError::Network(e.to_string()))?;
+
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+        return Ok(VersionFetch::Unchanged);
+    }
+    if !response.status().is_success() {
+        return Err(CogniaError::Network(format!(
+            "Failed to fetch Node.js versions: {}",
+            response.status()
+        )));
+    }
+
+    let header = |name: reqwest::header::HeaderName| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(String::from)
+    };
+    let etag = header(reqwest::header::ETAG);
+    let last_modified = header(reqwest::header::LAST_MODIFIED);
+
+    let index: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| CogniaError::Parse(e.to_string()))?;
+    Ok(VersionFetch::from_listing(
+        parse_node_dist_index(&index),
+        cursor,
+        etag,
+        last_modified,
+    ))
+}
+
 impl NodeProviderUtils {
     /// Execute a command and return stdout on success
     pub async fn run_command(cmd: &str, args: &[&str]) -> CogniaResult<String> {
@@ -494,6 +573,21 @@ async fn install_package_common(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_node_dist_index() {
+        let index = serde_json::json!([
+            {"version": "v22.1.0", "date": "2024-05-02", "lts": false},
+            {"version": "v20.13.0", "date": "2024-05-07", "lts": "Iron"},
+            {"date": "2024-01-01"}
+        ]);
+        let versions = parse_node_dist_index(&index);
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].version, "v22.1.0");
+        assert_eq!(versions[0].release_date.as_deref(), Some("2024-05-02"));
+        assert_eq!(versions[1].version, "v20.13.0");
+        assert!(parse_node_dist_index(&serde_json::json!({})).is_empty());
+    }
+
     #[test]
     fn test_build_package_spec() {
         assert_eq!(
@@ -12,7 +12,7 @@
 use crate::resolver::{Dependency, VersionConstraint};
 use async_trait::async_trait;
 use std::collections::HashSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 /// npm - Node Package Manager
@@ -161,6 +161,7 @@ fn parse_installed_output(
                 provider: self.id().into(),
                 installed_at: String::new(),
                 is_global: true,
+                kind: None,
             })
             .collect())
     }
@@ -221,6 +222,9 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::List,
             Capability::Update,
             Capability::Upgrade,
+            Capability::Info,
+            Capability::Versions,
+            Capability::SupportsPrefix,
         ])
     }
     fn supported_platforms(&self) -> Vec<Platform> {
@@ -417,6 +421,47 @@ async fn install(&self, req: InstallRequest) -> CogniaResult<InstallReceipt> {
         })
     }
 
+    /// `npm install -g --prefix` keeps the global layout inside the prefix,
+    /// so bins land in `<prefix>/bin` (or the prefix itself on Windows)
+    async fn install_to_prefix(
+        &self,
+        req: InstallRequest,
+        prefix: &Path,
+    ) -> CogniaResult<InstallReceipt> {
+        let pkg = if let Some(v) = &req.version {
+            format!("{}@{}", req.name, v)
+        } else {
+            req.name.clone()
+        };
+        let prefix_str = prefix.to_string_lossy().to_string();
+
+        let mut args = vec!["install", "-g", "--prefix", &prefix_str, &pkg];
+        if req.force {
+            args.push("--force");
+        }
+        self.run_npm(&args).await?;
+
+        let install_path = if cfg!(windows) {
+            prefix.join("node_modules").join(&req.name)
+        } else {
+            prefix.join("lib").join("node_modules").join(&req.name)
+        };
+        let actual_version = std::fs::read_to_string(install_path.join("package.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .and_then(|json| json["version"].as_str().map(String::from))
+            .unwrap_or_else(|| req.version.clone().unwrap_or_else(|| "unknown".into()));
+
+        Ok(InstallReceipt {
+            name: req.name,
+            version: actual_version,
+            provider: self.id().into(),
+            install_path,
+            files: vec![],
+            installed_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
     /// Get the installed version of a specific package
     async fn get_installed_version(&self, name: &str) -> CogniaResult<Option<String>> {
         match self.get_package_version(name, true).await {
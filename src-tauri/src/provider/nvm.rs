@@ -1,3 +1,4 @@
+use super::node_base::{fetch_node_dist_versions, NODE_DIST_URL};
 use super::traits::*;
 use crate::error::{CogniaError, CogniaResult};
 use crate::platform::{
@@ -5,12 +6,14 @@
     process::{self, ProcessOptions},
 };
 use async_trait::async_trait;
+use reqwest::Client;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 pub struct NvmProvider {
     nvm_dir: Option<PathBuf>,
+    client: Client,
 }
 
 impl NvmProvider {
@@ -20,7 +23,10 @@ pub fn new() -> Self {
             .map(PathBuf::from)
             .or_else(|| dirs_home().map(|h| h.join(".nvm")));
 
-        Self { nvm_dir }
+        Self {
+            nvm_dir,
+            client: crate::platform::proxy::get_client_for_provider("nvm"),
+        }
     }
 
     fn nvm_dir(&self) -> CogniaResult<&PathBuf> {
@@ -83,6 +89,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::VersionSwitch,
             Capability::MultiVersion,
             Capability::ProjectLocal,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
 
@@ -183,6 +191,18 @@ async fn get_versions(&self, _name: &str) -> CogniaResult<Vec<VersionInfo>> {
         Ok(versions)
     }
 
+    /// Diff the Node.js release index nvm installs from, honouring
+    /// `NVM_NODEJS_ORG_MIRROR`, instead of listing every remote version.
+    async fn fetch_versions_incremental(
+        &self,
+        _name: &str,
+        cursor: &VersionSyncCursor,
+    ) -> CogniaResult<VersionFetch> {
+        let dist_url =
+            std::env::var("NVM_NODEJS_ORG_MIRROR").unwrap_or_else(|_| NODE_DIST_URL.into());
+        fetch_node_dist_versions(&self.client, &dist_url, cursor).await
+    }
+
     async fn install(&self, request: InstallRequest) -> CogniaResult<InstallReceipt> {
         // nvm-windows uses "lts" without "--" prefix
         #[cfg(windows)]
@@ -254,6 +274,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                     install_path: nvm_dir.join("versions").join("node").join(version),
                     installed_at: String::new(),
                     is_global: true,
+                    kind: None,
                 })
             })
             .collect();
@@ -90,6 +90,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::Update,
             Capability::Upgrade,
             Capability::UpdateIndex,
+            Capability::Info,
+            Capability::RequiresElevation,
         ])
     }
 
@@ -295,6 +297,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                         install_path: PathBuf::from("/usr"),
                         installed_at: String::new(),
                         is_global: true,
+                        kind: None,
                     })
                 } else {
                     None
@@ -474,7 +477,7 @@ fn test_capabilities() {
         assert!(caps.contains(&Capability::Update));
         assert!(caps.contains(&Capability::Upgrade));
         assert!(caps.contains(&Capability::UpdateIndex));
-        assert_eq!(caps.len(), 7);
+        assert_eq!(caps.len(), 9);
     }
 
     #[test]
@@ -560,6 +563,7 @@ fn test_parse_pacman_query_output() {
                         install_path: PathBuf::from("/usr"),
                         installed_at: String::new(),
                         is_global: true,
+                        kind: None,
                     })
                 } else {
                     None
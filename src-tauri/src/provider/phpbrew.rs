@@ -132,6 +132,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::VersionSwitch,
             Capability::MultiVersion,
             Capability::ProjectLocal,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
 
@@ -289,6 +291,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                 install_path: v.install_path,
                 installed_at: String::new(),
                 is_global: true,
+                kind: None,
             })
             .collect();
 
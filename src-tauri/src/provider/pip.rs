@@ -8,7 +8,7 @@
 use crate::resolver::{Dependency, VersionConstraint};
 use async_trait::async_trait;
 use std::collections::HashSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 /// pip - Python package installer (direct, not via uv)
@@ -110,23 +110,31 @@ async fn run_pip_raw(&self, args: &[&str]) -> CogniaResult<String> {
     /// Get the installed version and location of a package using pip show
     async fn get_package_info_raw(&self, name: &str) -> CogniaResult<(String, PathBuf)> {
         let output = self.run_pip_raw(&["show", name]).await?;
+        parse_pip_show(&output)
+            .ok_or_else(|| CogniaError::Provider(format!("Package {} not found", name)))
+    }
 
-        let mut version = String::new();
-        let mut location = PathBuf::new();
-
-        for line in output.lines() {
-            if let Some(v) = line.strip_prefix("Version:") {
-                version = v.trim().to_string();
-            } else if let Some(loc) = line.strip_prefix("Location:") {
-                location = PathBuf::from(loc.trim());
-            }
+    /// Interpreter inside a virtualenv created at `venv`
+    fn venv_python(venv: &Path) -> PathBuf {
+        if cfg!(windows) {
+            venv.join("Scripts").join("python.exe")
+        } else {
+            venv.join("bin").join("python")
         }
+    }
 
-        if version.is_empty() {
-            return Err(CogniaError::Provider(format!("Package {} not found", name)));
-        }
+    /// Run `python -m pip` with a specific interpreter
+    async fn run_pip_with(python: &Path, args: &[String]) -> CogniaResult<String> {
+        let mut args_refs = vec!["-m", "pip"];
+        args_refs.extend(args.iter().map(|s| s.as_str()));
 
-        Ok((version, location))
+        let opts = ProcessOptions::new().with_timeout(Duration::from_secs(120));
+        let output = process::execute(&python.to_string_lossy(), &args_refs, Some(opts)).await?;
+        if output.success {
+            Ok(output.stdout)
+        } else {
+            Err(CogniaError::Provider(output.stderr))
+        }
     }
 
     /// Get only the installed version of a package
@@ -164,12 +172,58 @@ async fn get_global_site_packages(&self) -> Option<PathBuf> {
     }
 }
 
+/// Exit code used by [`IMPORT_CHECK_SCRIPT`] when the distribution is not found
+const IMPORT_CHECK_NOT_FOUND: i32 = 3;
+
+/// Import every top-level module declared by a distribution (argv[1])
+const IMPORT_CHECK_SCRIPT: &str = "\
+import importlib, importlib.metadata as md, sys
+try:
+    dist = md.distribution(sys.argv[1])
+except md.PackageNotFoundError:
+    sys.exit(3)
+for mod in (dist.read_text('top_level.txt') or '').split():
+    if mod and not mod.startswith('_'):
+        importlib.import_module(mod)
+";
+
+/// Interpreter used for import checks; pip installs into the same one on PATH
+async fn find_python() -> Option<&'static str> {
+    let candidates: &[&'static str] = if cfg!(windows) {
+        &["python", "py"]
+    } else {
+        &["python3", "python"]
+    };
+    for candidate in candidates {
+        if process::which(candidate).await.is_some() {
+            return Some(*candidate);
+        }
+    }
+    None
+}
+
 impl Default for PipProvider {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Version and location from `pip show` output
+fn parse_pip_show(output: &str) -> Option<(String, PathBuf)> {
+    let mut version = String::new();
+    let mut location = PathBuf::new();
+
+    for line in output.lines() {
+        if let Some(v) = line.strip_prefix("Version:") {
+            version = v.trim().to_string();
+        } else if let Some(loc) = line.strip_prefix("Location:") {
+            location = PathBuf::from(loc.trim());
+        }
+    }
+
+    (!version.is_empty()).then_some((version, location))
+}
+
 #[async_trait]
 impl Provider for PipProvider {
     fn id(&self) -> &str {
@@ -187,6 +241,9 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::Search,
             Capability::List,
             Capability::Update,
+            Capability::Info,
+            Capability::Versions,
+            Capability::SupportsPrefix,
         ])
     }
 
@@ -411,6 +468,40 @@ async fn get_installed_version(&self, name: &str) -> CogniaResult<Option<String>
         self.get_package_version(name).await.map(Some)
     }
 
+    async fn verify_installed(
+        &self,
+        name: &str,
+        expected_version: &str,
+    ) -> CogniaResult<PackageVerification> {
+        let installed = self.get_package_version(name).await?;
+        if !versions_match(&installed, expected_version) {
+            return Ok(PackageVerification::failed(
+                "installed_version",
+                format!(
+                    "Expected {} {} but {} is installed",
+                    name, expected_version, installed
+                ),
+            ));
+        }
+
+        let Some(python) = find_python().await else {
+            return Ok(PackageVerification::inconclusive(
+                "import",
+                "No Python interpreter found to run the import check",
+            ));
+        };
+        let opts = ProcessOptions::new().with_timeout(Duration::from_secs(60));
+        let out = process::execute(python, &["-c", IMPORT_CHECK_SCRIPT, name], Some(opts)).await?;
+        Ok(match out.exit_code {
+            0 => PackageVerification::passed("import"),
+            IMPORT_CHECK_NOT_FOUND => PackageVerification::inconclusive(
+                "import",
+                format!("{} is not visible to {}", name, python),
+            ),
+            _ => PackageVerification::failed("import", out.stderr.trim().to_string()),
+        })
+    }
+
     async fn install(&self, req: InstallRequest) -> CogniaResult<InstallReceipt> {
         let pkg = if let Some(v) = &req.version {
             format!("{}=={}", req.name, v)
@@ -446,6 +537,60 @@ async fn install(&self, req: InstallRequest) -> CogniaResult<InstallReceipt> {
         })
     }
 
+    /// Creates a virtualenv at `prefix` and installs into it, so console
+    /// scripts land in the venv's `bin`/`Scripts` directory
+    async fn install_to_prefix(
+        &self,
+        req: InstallRequest,
+        prefix: &Path,
+    ) -> CogniaResult<InstallReceipt> {
+        let python = if cfg!(windows) { "python" } else { "python3" };
+        let opts = ProcessOptions::new().with_timeout(Duration::from_secs(120));
+        let out = process::execute(
+            python,
+            &["-m", "venv", &prefix.to_string_lossy()],
+            Some(opts),
+        )
+        .await?;
+        if !out.success {
+            return Err(CogniaError::Provider(out.stderr));
+        }
+
+        let pkg = if let Some(v) = &req.version {
+            format!("{}=={}", req.name, v)
+        } else {
+            req.name.clone()
+        };
+        let venv_python = Self::venv_python(prefix);
+        let mut args = vec!["install"];
+        if req.force {
+            args.push("--force-reinstall");
+        }
+        args.push(&pkg);
+        Self::run_pip_with(&venv_python, &self.build_pip_args(&args)).await?;
+
+        let (actual_version, install_path) =
+            Self::run_pip_with(&venv_python, &["show".to_string(), req.name.clone()])
+                .await
+                .ok()
+                .and_then(|output| parse_pip_show(&output))
+                .unwrap_or_else(|| {
+                    (
+                        req.version.clone().unwrap_or_else(|| "unknown".into()),
+                        prefix.to_path_buf(),
+                    )
+                });
+
+        Ok(InstallReceipt {
+            name: req.name,
+            version: actual_version,
+            provider: self.id().into(),
+            install_path,
+            files: vec![],
+            installed_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
     async fn uninstall(&self, req: UninstallRequest) -> CogniaResult<()> {
         self.run_pip(&["uninstall", "-y", &req.name]).await?;
         Ok(())
@@ -484,6 +629,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                     install_path: site_packages.clone(),
                     installed_at: String::new(),
                     is_global: true,
+                    kind: None,
                 });
             }
 
@@ -626,6 +772,8 @@ fn test_capabilities() {
         assert!(caps.contains(&Capability::Search));
         assert!(caps.contains(&Capability::List));
         assert!(caps.contains(&Capability::Update));
+        assert!(caps.contains(&Capability::SupportsPrefix));
+        assert!(!caps.contains(&Capability::RequiresElevation));
     }
 
     #[test]
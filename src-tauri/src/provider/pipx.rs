@@ -90,6 +90,7 @@ fn parse_pipx_list_json(output: &str) -> Vec<InstalledPackage> {
                         install_path: venv_path,
                         installed_at: String::new(),
                         is_global: true,
+                        kind: None,
                     });
                 }
             }
@@ -120,6 +121,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::Search,
             Capability::List,
             Capability::Update,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
     fn supported_platforms(&self) -> Vec<Platform> {
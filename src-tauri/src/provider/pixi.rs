@@ -0,0 +1,482 @@
+use super::traits::*;
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::{
+    env::Platform,
+    process::{self, ProcessOptions},
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// pixi - Conda-compatible package and project environment manager (prefix.dev)
+///
+/// Project environments are described by `pixi.toml` / `pixi.lock`; globally
+/// exposed tools live in isolated environments managed via `pixi global`.
+/// This provider manages the global tools; project operations are exposed as
+/// dedicated commands in `commands::pixi`.
+pub struct PixiProvider;
+
+/// A globally installed pixi environment as reported by `pixi global list`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PixiGlobalEnv {
+    pub name: String,
+    pub version: Option<String>,
+    pub exposed: Vec<String>,
+}
+
+/// A package entry from `pixi list --json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PixiPackage {
+    pub name: String,
+    pub version: String,
+    pub build: Option<String>,
+    /// `conda` or `pypi`
+    pub kind: String,
+    pub source: Option<String>,
+    pub size_bytes: Option<u64>,
+    /// Whether the package is listed in the manifest (vs. pulled in transitively)
+    pub is_explicit: bool,
+}
+
+impl PixiProvider {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn run_pixi(&self, args: &[&str]) -> CogniaResult<String> {
+        let opts = ProcessOptions::new().with_timeout(Duration::from_secs(300));
+        let out = process::execute("pixi", args, Some(opts)).await?;
+        if out.success {
+            Ok(out.stdout)
+        } else {
+            Err(CogniaError::Provider(out.stderr))
+        }
+    }
+
+    /// Get the pixi home directory (`PIXI_HOME` or `~/.pixi`)
+    pub fn get_pixi_home() -> Option<PathBuf> {
+        std::env::var("PIXI_HOME")
+            .ok()
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|h| h.join(".pixi")))
+    }
+
+    fn global_env_path(name: &str) -> PathBuf {
+        Self::get_pixi_home()
+            .map(|p| p.join("envs").join(name))
+            .unwrap_or_else(|| PathBuf::from("pixi").join("envs").join(name))
+    }
+
+    fn to_installed_package(&self, env: PixiGlobalEnv) -> InstalledPackage {
+        InstalledPackage {
+            install_path: Self::global_env_path(&env.name),
+            name: env.name,
+            version: env.version.unwrap_or_default(),
+            provider: self.id().into(),
+            installed_at: String::new(),
+            is_global: true,
+            kind: None,
+        }
+    }
+}
+
+/// Parse the tree output of `pixi global list`.
+///
+/// ```text
+/// Global environments as specified in '/home/user/.pixi/manifests/pixi-global.toml'
+/// ├── bat: 0.24.0
+/// │   └─ exposes: bat
+/// └── python: 3.12.7
+///     └─ exposes: python, python3
+/// ```
+pub fn parse_global_list(output: &str) -> Vec<PixiGlobalEnv> {
+    let mut envs: Vec<PixiGlobalEnv> = Vec::new();
+
+    for line in output.lines() {
+        let entry = line
+            .trim_start_matches(|c: char| matches!(c, '│' | '├' | '└' | '─' | '|' | '`' | '-'))
+            .trim_start_matches(|c: char| c.is_whitespace() || matches!(c, '│' | '└' | '─'))
+            .trim();
+        if entry.is_empty() {
+            continue;
+        }
+
+        if let Some(exposed) = entry.strip_prefix("exposes:") {
+            if let Some(env) = envs.last_mut() {
+                env.exposed.extend(
+                    exposed
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|s| !s.is_empty())
+                        .map(str::to_string),
+                );
+            }
+            continue;
+        }
+
+        // Environment rows are the top-level tree items (`├──` / `└──`)
+        let is_env_row = line.starts_with('├') || line.starts_with('└');
+        if !is_env_row {
+            continue;
+        }
+
+        let (name, version) = match entry.split_once(':') {
+            Some((name, version)) => (name.trim(), Some(version.trim())),
+            None => match entry.split_once(' ') {
+                Some((name, version)) => (name.trim(), Some(version.trim())),
+                None => (entry, None),
+            },
+        };
+        if name.is_empty() {
+            continue;
+        }
+
+        envs.push(PixiGlobalEnv {
+            name: name.to_string(),
+            version: version.filter(|v| !v.is_empty()).map(str::to_string),
+            exposed: Vec::new(),
+        });
+    }
+
+    envs
+}
+
+/// Parse `pixi list --json` output.
+pub fn parse_list_json(output: &str) -> CogniaResult<Vec<PixiPackage>> {
+    let json: serde_json::Value = serde_json::from_str(output)
+        .map_err(|e| CogniaError::Parse(format!("Failed to parse pixi list: {}", e)))?;
+
+    let packages = json
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|pkg| {
+                    Some(PixiPackage {
+                        name: pkg["name"].as_str()?.to_string(),
+                        version: pkg["version"].as_str().unwrap_or("").to_string(),
+                        build: pkg["build"].as_str().map(|s| s.to_string()),
+                        kind: pkg["kind"].as_str().unwrap_or("conda").to_string(),
+                        source: pkg["source"].as_str().map(|s| s.to_string()),
+                        size_bytes: pkg["size_bytes"].as_u64(),
+                        is_explicit: pkg["is_explicit"].as_bool().unwrap_or(false),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(packages)
+}
+
+/// Parse the key/value blocks printed by `pixi search`.
+fn parse_search_output(output: &str) -> Vec<(String, String)> {
+    let mut results = Vec::new();
+    let mut name: Option<String> = None;
+
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some(value) = line.strip_prefix("Name") {
+            name = Some(value.trim().to_string());
+        } else if let Some(value) = line.strip_prefix("Version") {
+            if let Some(n) = name.take() {
+                results.push((n, value.trim().to_string()));
+            }
+        }
+    }
+
+    results
+}
+
+impl Default for PixiProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Provider for PixiProvider {
+    fn id(&self) -> &str {
+        "pixi"
+    }
+    fn display_name(&self) -> &str {
+        "pixi (Conda-compatible Environment Manager)"
+    }
+    fn capabilities(&self) -> HashSet<Capability> {
+        HashSet::from([
+            Capability::Install,
+            Capability::Uninstall,
+            Capability::Search,
+            Capability::List,
+            Capability::Update,
+            Capability::Info,
+            Capability::Versions,
+        ])
+    }
+    fn supported_platforms(&self) -> Vec<Platform> {
+        vec![Platform::Windows, Platform::MacOS, Platform::Linux]
+    }
+    fn priority(&self) -> i32 {
+        79
+    }
+
+    async fn is_available(&self) -> bool {
+        if process::which("pixi").await.is_none() {
+            return false;
+        }
+        match process::execute("pixi", &["--version"], None).await {
+            Ok(output) => output.success && !output.stdout.is_empty(),
+            Err(_) => false,
+        }
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        options: SearchOptions,
+    ) -> CogniaResult<Vec<PackageSummary>> {
+        let limit = options.limit.unwrap_or(20);
+        let out = self.run_pixi(&["search", query]).await?;
+
+        Ok(parse_search_output(&out)
+            .into_iter()
+            .take(limit)
+            .map(|(name, version)| PackageSummary {
+                name,
+                description: None,
+                latest_version: Some(version),
+                provider: self.id().into(),
+            })
+            .collect())
+    }
+
+    async fn get_package_info(&self, name: &str) -> CogniaResult<PackageInfo> {
+        let versions = self
+            .run_pixi(&["search", name])
+            .await
+            .map(|out| {
+                parse_search_output(&out)
+                    .into_iter()
+                    .filter(|(n, _)| n == name)
+                    .map(|(_, version)| VersionInfo {
+                        version,
+                        release_date: None,
+                        deprecated: false,
+                        yanked: false,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(PackageInfo {
+            name: name.into(),
+            display_name: Some(name.into()),
+            description: None,
+            homepage: Some(format!(
+                "https://prefix.dev/channels/conda-forge/packages/{}",
+                name
+            )),
+            license: None,
+            repository: None,
+            versions,
+            provider: self.id().into(),
+        })
+    }
+
+    async fn get_versions(&self, name: &str) -> CogniaResult<Vec<VersionInfo>> {
+        let info = self.get_package_info(name).await?;
+        Ok(info.versions)
+    }
+
+    async fn install(&self, req: InstallRequest) -> CogniaResult<InstallReceipt> {
+        let pkg = if let Some(v) = &req.version {
+            format!("{}=={}", req.name, v)
+        } else {
+            req.name.clone()
+        };
+
+        self.run_pixi(&["global", "install", &pkg]).await?;
+
+        let actual_version = self
+            .get_installed_version(&req.name)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| req.version.clone().unwrap_or_else(|| "unknown".into()));
+
+        Ok(InstallReceipt {
+            install_path: Self::global_env_path(&req.name),
+            name: req.name,
+            version: actual_version,
+            provider: self.id().into(),
+            files: vec![],
+            installed_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    async fn get_installed_version(&self, name: &str) -> CogniaResult<Option<String>> {
+        let out = self.run_pixi(&["global", "list"]).await?;
+        Ok(parse_global_list(&out)
+            .into_iter()
+            .find(|env| env.name.eq_ignore_ascii_case(name))
+            .and_then(|env| env.version))
+    }
+
+    async fn uninstall(&self, req: UninstallRequest) -> CogniaResult<()> {
+        self.run_pixi(&["global", "uninstall", &req.name]).await?;
+        Ok(())
+    }
+
+    async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<InstalledPackage>> {
+        let out = self.run_pixi(&["global", "list"]).await?;
+        let mut packages: Vec<InstalledPackage> = parse_global_list(&out)
+            .into_iter()
+            .map(|env| self.to_installed_package(env))
+            .collect();
+
+        if let Some(ref name_filter) = filter.name_filter {
+            packages.retain(|p| p.name.contains(name_filter));
+        }
+
+        Ok(packages)
+    }
+
+    async fn check_updates(&self, packages: &[String]) -> CogniaResult<Vec<UpdateInfo>> {
+        let installed = self.list_installed(InstalledFilter::default()).await?;
+        let mut updates = Vec::new();
+
+        for pkg in &installed {
+            if !packages.is_empty() && !packages.contains(&pkg.name) {
+                continue;
+            }
+
+            let latest = match self.run_pixi(&["search", &pkg.name]).await {
+                Ok(out) => parse_search_output(&out)
+                    .into_iter()
+                    .find(|(n, _)| *n == pkg.name)
+                    .map(|(_, v)| v),
+                Err(_) => None,
+            };
+
+            if let Some(latest) = latest {
+                if !pkg.version.is_empty() && latest != pkg.version {
+                    updates.push(UpdateInfo {
+                        name: pkg.name.clone(),
+                        current_version: pkg.version.clone(),
+                        latest_version: latest,
+                        provider: self.id().into(),
+                    });
+                }
+            }
+        }
+
+        Ok(updates)
+    }
+}
+
+#[async_trait]
+impl SystemPackageProvider for PixiProvider {
+    async fn check_system_requirements(&self) -> CogniaResult<bool> {
+        Ok(self.is_available().await)
+    }
+
+    fn requires_elevation(&self, _operation: &str) -> bool {
+        false
+    }
+
+    async fn get_version(&self) -> CogniaResult<String> {
+        let out = self.run_pixi(&["--version"]).await?;
+        // Output: "pixi 0.39.5"
+        Ok(out
+            .split_whitespace()
+            .last()
+            .unwrap_or("unknown")
+            .to_string())
+    }
+
+    async fn get_executable_path(&self) -> CogniaResult<PathBuf> {
+        process::which("pixi")
+            .await
+            .map(PathBuf::from)
+            .ok_or_else(|| CogniaError::Provider("pixi not found".into()))
+    }
+
+    fn get_install_instructions(&self) -> Option<String> {
+        Some("Install pixi: https://pixi.sh/latest/#installation".into())
+    }
+
+    async fn is_package_installed(&self, name: &str) -> CogniaResult<bool> {
+        Ok(self.get_installed_version(name).await?.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_metadata() {
+        let provider = PixiProvider::new();
+        assert_eq!(provider.id(), "pixi");
+        assert_eq!(provider.priority(), 79);
+        assert!(provider.supported_platforms().contains(&Platform::Linux));
+        assert!(provider.capabilities().contains(&Capability::Install));
+    }
+
+    #[test]
+    fn test_parse_global_list() {
+        let output =
+            "Global environments as specified in '/home/u/.pixi/manifests/pixi-global.toml'\n\
+├── bat: 0.24.0\n\
+│   └─ exposes: bat\n\
+└── python: 3.12.7\n    \
+└─ exposes: python, python3\n";
+        let envs = parse_global_list(output);
+        assert_eq!(envs.len(), 2);
+        assert_eq!(envs[0].name, "bat");
+        assert_eq!(envs[0].version.as_deref(), Some("0.24.0"));
+        assert_eq!(envs[0].exposed, vec!["bat"]);
+        assert_eq!(envs[1].name, "python");
+        assert_eq!(envs[1].exposed, vec!["python", "python3"]);
+    }
+
+    #[test]
+    fn test_parse_global_list_empty() {
+        assert!(parse_global_list("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_list_json() {
+        let output = r#"[
+            {"name":"python","version":"3.12.3","build":"hab00c5b_0_cpython","size_bytes":31991522,"kind":"conda","source":"https://conda.anaconda.org/conda-forge/","is_explicit":true},
+            {"name":"requests","version":"2.32.3","kind":"pypi","is_explicit":false}
+        ]"#;
+        let packages = parse_list_json(output).unwrap();
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[0].name, "python");
+        assert!(packages[0].is_explicit);
+        assert_eq!(packages[0].size_bytes, Some(31991522));
+        assert_eq!(packages[1].kind, "pypi");
+        assert!(packages[1].build.is_none());
+    }
+
+    #[test]
+    fn test_parse_list_json_invalid() {
+        assert!(parse_list_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_parse_search_output() {
+        let output = "Using channels: conda-forge\n\n\
+python-3.12.3-hab00c5b_0_cpython\n\
+--------------------------------\n\n\
+Name                python\n\
+Version             3.12.3\n\
+Build               hab00c5b_0_cpython\n";
+        let results = parse_search_output(output);
+        assert_eq!(results, vec![("python".to_string(), "3.12.3".to_string())]);
+    }
+}
@@ -154,6 +154,7 @@ fn parse_installed_output(
                 provider: self.id().into(),
                 installed_at: String::new(),
                 is_global: true,
+                kind: None,
             })
             .collect())
     }
@@ -214,6 +215,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::List,
             Capability::Update,
             Capability::Upgrade,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
     fn supported_platforms(&self) -> Vec<Platform> {
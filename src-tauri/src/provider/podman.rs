@@ -202,6 +202,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::List,
             Capability::Update,
             Capability::Upgrade,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
 
@@ -532,6 +534,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                     install_path: PathBuf::new(),
                     installed_at: created,
                     is_global: true,
+                    kind: None,
                 })
             })
             .collect();
@@ -102,6 +102,7 @@ fn parse_poetry_show_output(output: &str) -> Vec<InstalledPackage> {
                     install_path,
                     installed_at: String::new(),
                     is_global: false,
+                    kind: None,
                 });
             }
         }
@@ -150,6 +151,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::Update,
             Capability::LockVersion,
             Capability::ProjectLocal,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
 
@@ -96,6 +96,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::Search,
             Capability::List,
             Capability::Update,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
     fn supported_platforms(&self) -> Vec<Platform> {
@@ -300,6 +302,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                         install_path,
                         installed_at: String::new(),
                         is_global: true,
+                        kind: None,
                     })
                 } else {
                     None
@@ -21,7 +21,7 @@ pub struct PubProvider {
 impl PubProvider {
     pub fn new() -> Self {
         Self {
-            client: crate::platform::proxy::get_shared_client(),
+            client: crate::platform::proxy::get_client_for_provider("pub"),
         }
     }
 
@@ -130,6 +130,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::Uninstall,
             Capability::Search,
             Capability::List,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
 
@@ -338,6 +340,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                 install_path: PathBuf::from(""),
                 installed_at: String::new(),
                 is_global: true,
+                kind: None,
             });
         }
 
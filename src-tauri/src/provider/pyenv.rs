@@ -13,14 +13,130 @@ pub struct PyenvProvider {
     pyenv_root: Option<PathBuf>,
 }
 
-impl PyenvProvider {
-    pub fn new() -> Self {
-        let pyenv_root = std::env::var("PYENV_ROOT")
+/// Resolve the pyenv root. `PYENV_ROOT` wins everywhere; pyenv-win also sets
+/// `PYENV`/`PYENV_HOME` and installs into `~/.pyenv/pyenv-win`.
+pub fn pyenv_root_dir() -> Option<PathBuf> {
+    let from_env = |key: &str| {
+        std::env::var(key)
             .ok()
+            .filter(|v| !v.trim().is_empty())
             .map(PathBuf::from)
-            .or_else(|| dirs_home().map(|h| h.join(".pyenv")));
+    };
+
+    if let Some(root) = from_env("PYENV_ROOT") {
+        return Some(root);
+    }
+    if cfg!(windows) {
+        from_env("PYENV")
+            .or_else(|| from_env("PYENV_HOME"))
+            .or_else(|| dirs_home().map(|h| h.join(".pyenv").join("pyenv-win")))
+    } else {
+        dirs_home().map(|h| h.join(".pyenv"))
+    }
+}
+
+/// Directory holding pyenv's shims (`$PYENV_ROOT/shims`)
+pub fn pyenv_shims_dir() -> Option<PathBuf> {
+    pyenv_root_dir().map(|root| root.join("shims"))
+}
+
+/// Whether `path` (a binary or directory) lives directly in pyenv's shim directory
+pub fn is_in_pyenv_shims(path: &Path, shims: &Path) -> bool {
+    let normalize = |p: &Path| {
+        let s = p.to_string_lossy().replace('\\', "/");
+        let s = s.trim_end_matches('/').to_string();
+        if cfg!(windows) {
+            s.to_ascii_lowercase()
+        } else {
+            s
+        }
+    };
+    let shims = normalize(shims);
+    let target = normalize(path);
+    target == shims
+        || path
+            .parent()
+            .is_some_and(|parent| normalize(parent) == shims)
+}
+
+/// Whether the `tool` resolved from PATH is one of pyenv's shims
+pub async fn is_pyenv_managed(tool: &str) -> bool {
+    let Some(shims) = pyenv_shims_dir() else {
+        return false;
+    };
+    match process::which(tool).await {
+        Some(path) => is_in_pyenv_shims(Path::new(&path), &shims),
+        None => false,
+    }
+}
+
+/// Installable versions from `pyenv install --list`. Skips the
+/// `Available versions:` header and non-CPython distributions.
+pub fn parse_install_list(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter(|line| line.starts_with(|c: char| c.is_ascii_digit()))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Installed versions from `pyenv versions --bare`. Virtualenvs created by
+/// pyenv-virtualenv are listed as `<version>/envs/<name>` and are skipped.
+pub fn parse_bare_versions(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.contains("/envs/"))
+        .map(str::to_string)
+        .collect()
+}
+
+fn stable_series(version: &str) -> Option<(u32, u32)> {
+    // Only pure stable version numbers like "3.x.y"; pyenv-win suffixes
+    // (-win32, -arm) and pre-releases are skipped.
+    if !version.starts_with(|c: char| c.is_ascii_digit())
+        || version.contains('-')
+        || version.contains('a')
+        || version.contains('b')
+        || version.contains("rc")
+        || version.ends_with('t')
+    {
+        return None;
+    }
+    let mut parts = version.splitn(3, '.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
 
-        Self { pyenv_root }
+/// Pair each installed version with the newest patch release of its series
+pub fn find_patch_updates(installed: &[String], available: &[String]) -> Vec<(String, String)> {
+    let mut latest_per_series: std::collections::HashMap<(u32, u32), &str> =
+        std::collections::HashMap::new();
+    for version in available {
+        if let Some(series) = stable_series(version) {
+            // pyenv install --list is sorted ascending, so last seen wins
+            latest_per_series.insert(series, version);
+        }
+    }
+
+    installed
+        .iter()
+        .filter_map(|version| {
+            let series = stable_series(version)?;
+            let latest = latest_per_series.get(&series)?;
+            (*latest != version.as_str()).then(|| (version.clone(), latest.to_string()))
+        })
+        .collect()
+}
+
+impl PyenvProvider {
+    pub fn new() -> Self {
+        Self {
+            pyenv_root: pyenv_root_dir(),
+        }
     }
 
     fn pyenv_root(&self) -> CogniaResult<&PathBuf> {
@@ -29,18 +145,15 @@ fn pyenv_root(&self) -> CogniaResult<&PathBuf> {
             .ok_or_else(|| CogniaError::Provider("PYENV_ROOT not found".into()))
     }
 
-    async fn run_pyenv(&self, args: &[&str]) -> CogniaResult<String> {
-        let opts = ProcessOptions::new().with_timeout(Duration::from_secs(120));
-        let output = process::execute("pyenv", args, Some(opts)).await?;
-        if output.success {
-            Ok(output.stdout)
-        } else {
-            Err(CogniaError::Provider(output.stderr))
+    fn make_opts(&self, timeout: Duration) -> ProcessOptions {
+        let opts = ProcessOptions::new().with_timeout(timeout);
+        match &self.pyenv_root {
+            Some(root) => opts.with_env("PYENV_ROOT", root.to_string_lossy()),
+            None => opts,
         }
     }
 
-    async fn run_pyenv_long(&self, args: &[&str]) -> CogniaResult<String> {
-        let opts = ProcessOptions::new().with_timeout(Duration::from_secs(900));
+    async fn run_pyenv_with(&self, args: &[&str], opts: ProcessOptions) -> CogniaResult<String> {
         let output = process::execute("pyenv", args, Some(opts)).await?;
         if output.success {
             Ok(output.stdout)
@@ -48,6 +161,16 @@ async fn run_pyenv_long(&self, args: &[&str]) -> CogniaResult<String> {
             Err(CogniaError::Provider(output.stderr))
         }
     }
+
+    async fn run_pyenv(&self, args: &[&str]) -> CogniaResult<String> {
+        self.run_pyenv_with(args, self.make_opts(Duration::from_secs(120)))
+            .await
+    }
+
+    async fn run_pyenv_long(&self, args: &[&str]) -> CogniaResult<String> {
+        self.run_pyenv_with(args, self.make_opts(Duration::from_secs(900)))
+            .await
+    }
 }
 
 impl Default for PyenvProvider {
@@ -76,6 +199,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::VersionSwitch,
             Capability::MultiVersion,
             Capability::ProjectLocal,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
 
@@ -105,21 +230,13 @@ async fn search(
     ) -> CogniaResult<Vec<PackageSummary>> {
         let output = self.run_pyenv(&["install", "--list"]).await?;
 
-        let versions: Vec<PackageSummary> = output
-            .lines()
-            .map(|line| line.trim())
-            .filter(|line| !line.is_empty() && !line.starts_with('#'))
-            .filter(|line| line.contains(query) || query.is_empty())
-            .filter(|line| {
-                line.chars()
-                    .next()
-                    .map(|c| c.is_ascii_digit())
-                    .unwrap_or(false)
-            })
+        let versions: Vec<PackageSummary> = parse_install_list(&output)
+            .into_iter()
+            .filter(|version| query.is_empty() || version.contains(query))
             .map(|version| PackageSummary {
                 name: format!("python@{}", version),
                 description: Some("Python programming language".into()),
-                latest_version: Some(version.to_string()),
+                latest_version: Some(version),
                 provider: self.id().to_string(),
             })
             .collect();
@@ -145,18 +262,10 @@ async fn get_package_info(&self, _name: &str) -> CogniaResult<PackageInfo> {
     async fn get_versions(&self, _name: &str) -> CogniaResult<Vec<VersionInfo>> {
         let output = self.run_pyenv(&["install", "--list"]).await?;
 
-        let versions: Vec<VersionInfo> = output
-            .lines()
-            .map(|line| line.trim())
-            .filter(|line| !line.is_empty() && !line.starts_with('#'))
-            .filter(|line| {
-                line.chars()
-                    .next()
-                    .map(|c| c.is_ascii_digit())
-                    .unwrap_or(false)
-            })
+        let versions: Vec<VersionInfo> = parse_install_list(&output)
+            .into_iter()
             .map(|version| VersionInfo {
-                version: version.to_string(),
+                version,
                 release_date: None,
                 deprecated: false,
                 yanked: false,
@@ -166,6 +275,17 @@ async fn get_versions(&self, _name: &str) -> CogniaResult<Vec<VersionInfo>> {
         Ok(versions)
     }
 
+    /// `pyenv install --list` can only be read whole, so diff it against the
+    /// cursor rather than handing back the complete list every sync.
+    async fn fetch_versions_incremental(
+        &self,
+        name: &str,
+        cursor: &VersionSyncCursor,
+    ) -> CogniaResult<VersionFetch> {
+        let listing = self.get_versions(name).await?;
+        Ok(VersionFetch::from_listing(listing, cursor, None, None))
+    }
+
     async fn install(&self, request: InstallRequest) -> CogniaResult<InstallReceipt> {
         self.install_with_progress(request, None).await
     }
@@ -266,17 +386,16 @@ async fn list_installed(
         let output = self.run_pyenv(&["versions", "--bare"]).await?;
         let pyenv_root = self.pyenv_root()?;
 
-        let packages: Vec<InstalledPackage> = output
-            .lines()
-            .map(|line| line.trim())
-            .filter(|line| !line.is_empty())
+        let packages: Vec<InstalledPackage> = parse_bare_versions(&output)
+            .into_iter()
             .map(|version| InstalledPackage {
                 name: "python".to_string(),
-                version: version.to_string(),
+                install_path: pyenv_root.join("versions").join(&version),
+                version,
                 provider: self.id().to_string(),
-                install_path: pyenv_root.join("versions").join(version),
                 installed_at: String::new(),
                 is_global: true,
+                kind: None,
             })
             .collect();
 
@@ -294,56 +413,18 @@ async fn check_updates(&self, _packages: &[String]) -> CogniaResult<Vec<UpdateIn
             .await
             .unwrap_or_default();
 
-        // Build a map of (major, minor) -> latest_patch for stable CPython versions
-        let mut latest_per_series: std::collections::HashMap<(u32, u32), String> =
-            std::collections::HashMap::new();
-
-        for line in available.lines() {
-            let v = line.trim();
-            if v.is_empty() {
-                continue;
-            }
-            // Only match pure stable version numbers like "3.x.y"
-            if !v.chars().next().is_some_and(|c| c.is_ascii_digit())
-                || v.contains('-')
-                || v.contains('a')
-                || v.contains('b')
-                || v.contains("rc")
-                || v.ends_with('t')
-            {
-                continue;
-            }
-            let parts: Vec<&str> = v.splitn(3, '.').collect();
-            if parts.len() >= 2 {
-                if let (Ok(major), Ok(minor)) = (parts[0].parse::<u32>(), parts[1].parse::<u32>()) {
-                    // pyenv install --list is sorted ascending, so last seen wins
-                    latest_per_series.insert((major, minor), v.to_string());
-                }
-            }
-        }
-
-        let mut updates = Vec::new();
-        for line in installed.lines() {
-            let version = line.trim();
-            if version.is_empty() || !version.chars().next().is_some_and(|c| c.is_ascii_digit()) {
-                continue;
-            }
-            let parts: Vec<&str> = version.splitn(3, '.').collect();
-            if parts.len() >= 2 {
-                if let (Ok(major), Ok(minor)) = (parts[0].parse::<u32>(), parts[1].parse::<u32>()) {
-                    if let Some(latest) = latest_per_series.get(&(major, minor)) {
-                        if latest != version {
-                            updates.push(UpdateInfo {
-                                name: "python".into(),
-                                current_version: version.to_string(),
-                                latest_version: latest.clone(),
-                                provider: self.id().into(),
-                            });
-                        }
-                    }
-                }
-            }
-        }
+        let updates = find_patch_updates(
+            &parse_bare_versions(&installed),
+            &parse_install_list(&available),
+        )
+        .into_iter()
+        .map(|(current_version, latest_version)| UpdateInfo {
+            name: "python".into(),
+            current_version,
+            latest_version,
+            provider: self.id().into(),
+        })
+        .collect();
 
         Ok(updates)
     }
@@ -356,16 +437,14 @@ async fn list_installed_versions(&self) -> CogniaResult<Vec<InstalledVersion>> {
         let current = self.get_current_version().await?.unwrap_or_default();
         let pyenv_root = self.pyenv_root()?;
 
-        let versions: Vec<InstalledVersion> = output
-            .lines()
-            .map(|line| line.trim())
-            .filter(|line| !line.is_empty())
+        let versions: Vec<InstalledVersion> = parse_bare_versions(&output)
+            .into_iter()
             .map(|version| InstalledVersion {
-                version: version.to_string(),
-                install_path: pyenv_root.join("versions").join(version),
+                install_path: pyenv_root.join("versions").join(&version),
                 size: None,
                 installed_at: None,
                 is_current: version == current,
+                version,
             })
             .collect();
 
@@ -389,8 +468,20 @@ async fn set_global_version(&self, version: &str) -> CogniaResult<()> {
     }
 
     async fn set_local_version(&self, project_path: &Path, version: &str) -> CogniaResult<()> {
-        let version_file = project_path.join(self.version_file_name());
-        crate::platform::fs::write_file_string(&version_file, version).await?;
+        let opts = self
+            .make_opts(Duration::from_secs(120))
+            .with_cwd(project_path.to_string_lossy());
+        if let Err(e) = self.run_pyenv_with(&["local", version], opts).await {
+            // `pyenv local` refuses versions that aren't installed yet; pinning
+            // ahead of installation is still valid, so write the file directly.
+            log::debug!(
+                "pyenv local {} failed, writing version file: {}",
+                version,
+                e
+            );
+            let version_file = project_path.join(self.version_file_name());
+            crate::platform::fs::write_file_string(&version_file, version).await?;
+        }
         Ok(())
     }
 
@@ -582,4 +673,57 @@ fn test_version_file_name() {
         let provider = PyenvProvider::new();
         assert_eq!(provider.version_file_name(), ".python-version");
     }
+
+    #[test]
+    fn test_parse_install_list() {
+        let output = "Available versions:\n  2.7.18\n  3.11.9\n  3.12.0a1\n  3.12.4\n  3.13.0t\n  anaconda3-2024.02-1\n  pypy3.10-7.3.16\n";
+        assert_eq!(
+            parse_install_list(output),
+            vec!["2.7.18", "3.11.9", "3.12.0a1", "3.12.4", "3.13.0t"]
+        );
+
+        // pyenv-win prints bare versions with architecture suffixes
+        let output = ":: [Info] ::  Mirror: https://www.python.org/ftp/python\n3.12.4-win32\n3.12.4-arm\n3.12.4\n";
+        assert_eq!(
+            parse_install_list(output),
+            vec!["3.12.4-win32", "3.12.4-arm", "3.12.4"]
+        );
+    }
+
+    #[test]
+    fn test_parse_bare_versions_skips_virtualenvs() {
+        let output = "3.11.9\n3.11.9/envs/tools\n3.12.4\ntools\n\n";
+        assert_eq!(
+            parse_bare_versions(output),
+            vec!["3.11.9", "3.12.4", "tools"]
+        );
+    }
+
+    #[test]
+    fn test_find_patch_updates() {
+        let available =
+            parse_install_list("3.11.8\n3.11.9\n3.12.3\n3.12.4\n3.12.4-win32\n3.13.0rc1\n3.13.0\n");
+        let installed = parse_bare_versions("3.11.8\n3.12.4\n3.13.0rc1\nmyenv\n");
+
+        let updates = find_patch_updates(&installed, &available);
+        assert_eq!(updates, vec![("3.11.8".to_string(), "3.11.9".to_string())]);
+    }
+
+    #[test]
+    fn test_is_in_pyenv_shims() {
+        let shims = PathBuf::from("/home/user/.pyenv/shims");
+        assert!(is_in_pyenv_shims(
+            Path::new("/home/user/.pyenv/shims/python"),
+            &shims
+        ));
+        assert!(is_in_pyenv_shims(
+            Path::new("/home/user/.pyenv/shims/"),
+            &shims
+        ));
+        assert!(!is_in_pyenv_shims(Path::new("/usr/bin/python3"), &shims));
+        assert!(!is_in_pyenv_shims(
+            Path::new("/home/user/.pyenv/versions/3.12.4/bin/python"),
+            &shims
+        ));
+    }
 }
@@ -0,0 +1,585 @@
+//! Rate-limit tracking and ETag conditional requests for the GitHub and
+//! GitLab REST clients
+
+use crate::cache::MetadataCache;
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::network::{HttpClient, NetworkError, RequestOptions};
+use once_cell::sync::Lazy;
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex as StdMutex};
+use tokio::sync::Mutex;
+
+/// Longest a queued request waits for the window to reset before failing
+const MAX_QUEUE_WAIT_SECS: u64 = 15 * 60;
+/// ETag entries outlive the usual metadata TTL, since the server confirms
+/// their freshness on every use
+const CONDITIONAL_CACHE_TTL_SECS: i64 = 7 * 24 * 3600;
+
+static RATE_LIMITS: Lazy<StdMutex<HashMap<RateLimitBucket, RateLimitStatus>>> =
+    Lazy::new(|| StdMutex::new(HashMap::new()));
+static QUEUES: Lazy<StdMutex<HashMap<RateLimitBucket, Arc<Mutex<()>>>>> =
+    Lazy::new(|| StdMutex::new(HashMap::new()));
+
+/// One quota window. APIs meter each credential and resource separately, so
+/// an exhausted anonymous `search` quota must not hold up token-backed `core`
+/// requests.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RateLimitBucket {
+    pub provider: String,
+    /// Quota bucket, e.g. `core` or `search`
+    pub resource: String,
+    /// Hash of the API host and token the quota is charged to
+    pub account: String,
+}
+
+impl RateLimitBucket {
+    pub fn new(provider: &str, resource: &str, host: &str, token: Option<&str>) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(host.as_bytes());
+        hasher.update([0]);
+        hasher.update(token.unwrap_or_default().as_bytes());
+        let account = format!(
+            "{}{}",
+            if token.is_some() {
+                "token-"
+            } else {
+                "anonymous-"
+            },
+            &hex::encode(hasher.finalize())[..16]
+        );
+        Self {
+            provider: provider.to_string(),
+            resource: resource.to_string(),
+            account,
+        }
+    }
+}
+
+/// The quota bucket a GitHub-style API meters `url` against
+pub fn resource_for_url(url: &str) -> &'static str {
+    let path = url
+        .split_once("://")
+        .map_or(url, |(_, rest)| rest.find('/').map_or("", |i| &rest[i..]));
+    if path.contains("/search/code") {
+        "code_search"
+    } else if path.contains("/search/") {
+        "search"
+    } else if path.starts_with("/graphql") || path.contains("/api/graphql") {
+        "graphql"
+    } else {
+        "core"
+    }
+}
+
+fn url_host(url: &str) -> &str {
+    url.split_once("://")
+        .map_or(url, |(_, rest)| rest.split('/').next().unwrap_or(rest))
+}
+
+/// What to do when the quota is exhausted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RateLimitPolicy {
+    /// Hold the request until the window resets
+    #[default]
+    Queue,
+    /// Return [`CogniaError::RateLimited`] right away, for interactive calls
+    FailFast,
+}
+
+/// Last quota reported by an API, plus local queue and cache counters
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitStatus {
+    pub provider: String,
+    pub limit: Option<u64>,
+    pub remaining: Option<u64>,
+    pub used: Option<u64>,
+    /// Unix time at which the window resets
+    pub reset_at: Option<u64>,
+    /// Quota bucket the numbers belong to, e.g. `core` or `search`
+    pub resource: Option<String>,
+    /// Unix time of the response the numbers came from
+    pub observed_at: Option<u64>,
+    /// Requests currently waiting for the window to reset
+    pub queued: usize,
+    /// Responses answered with 304 and served from the conditional cache
+    pub conditional_hits: u64,
+}
+
+impl RateLimitStatus {
+    /// Seconds until requests may be sent again, if the quota is used up
+    pub fn wait_secs(&self, now: u64) -> Option<u64> {
+        match (self.remaining, self.reset_at) {
+            (Some(0), Some(reset_at)) if reset_at > now => Some(reset_at - now),
+            _ => None,
+        }
+    }
+}
+
+/// A decoded API response and whether it came from the conditional cache
+#[derive(Debug, Clone)]
+pub struct ApiResponse<T> {
+    pub data: T,
+    pub from_cache: bool,
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn header_u64(headers: &HeaderMap, names: &[&str]) -> Option<u64> {
+    names.iter().find_map(|name| {
+        headers
+            .get(*name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse().ok())
+    })
+}
+
+/// Read GitHub `X-RateLimit-*` or GitLab `RateLimit-*` headers
+pub fn parse_rate_limit_headers(headers: &HeaderMap) -> Option<RateLimitStatus> {
+    let limit = header_u64(headers, &["x-ratelimit-limit", "ratelimit-limit"]);
+    let remaining = header_u64(headers, &["x-ratelimit-remaining", "ratelimit-remaining"]);
+    if limit.is_none() && remaining.is_none() {
+        return None;
+    }
+
+    Some(RateLimitStatus {
+        limit,
+        remaining,
+        used: header_u64(headers, &["x-ratelimit-used", "ratelimit-observed"]),
+        reset_at: header_u64(headers, &["x-ratelimit-reset", "ratelimit-reset"]),
+        resource: headers
+            .get("x-ratelimit-resource")
+            .and_then(|v| v.to_str().ok())
+            .map(String::from),
+        ..RateLimitStatus::default()
+    })
+}
+
+fn update_status(bucket: &RateLimitBucket, update: impl FnOnce(&mut RateLimitStatus)) {
+    if let Ok(mut limits) = RATE_LIMITS.lock() {
+        let status = limits
+            .entry(bucket.clone())
+            .or_insert_with(|| RateLimitStatus {
+                provider: bucket.provider.clone(),
+                resource: Some(bucket.resource.clone()),
+                ..RateLimitStatus::default()
+            });
+        update(status);
+    }
+}
+
+/// Remember the quota a response reported
+pub fn record_headers(bucket: &RateLimitBucket, headers: &HeaderMap) {
+    let Some(parsed) = parse_rate_limit_headers(headers) else {
+        return;
+    };
+    update_status(bucket, |status| {
+        status.limit = parsed.limit;
+        status.remaining = parsed.remaining;
+        status.used = parsed.used;
+        status.reset_at = parsed.reset_at;
+        if parsed.resource.is_some() {
+            status.resource = parsed.resource;
+        }
+        status.observed_at = Some(now_secs());
+    });
+}
+
+fn record_exhausted(bucket: &RateLimitBucket, retry_after: u64) {
+    let now = now_secs();
+    update_status(bucket, |status| {
+        status.remaining = Some(0);
+        status.reset_at = Some(now + retry_after);
+        status.observed_at = Some(now);
+    });
+}
+
+/// The quota last seen for `bucket`
+pub fn rate_limit_status(bucket: &RateLimitBucket) -> Option<RateLimitStatus> {
+    RATE_LIMITS
+        .lock()
+        .ok()
+        .and_then(|limits| limits.get(bucket).cloned())
+}
+
+fn rate_limited(provider: &str, retry_after: u64) -> CogniaError {
+    CogniaError::RateLimited {
+        provider: provider.to_string(),
+        retry_after,
+    }
+}
+
+/// Wait for the quota window when it is exhausted, or fail under
+/// [`RateLimitPolicy::FailFast`]. Queued requests go out one at a time.
+pub async fn acquire(bucket: &RateLimitBucket, policy: RateLimitPolicy) -> CogniaResult<()> {
+    let Some(wait) = rate_limit_status(bucket).and_then(|s| s.wait_secs(now_secs())) else {
+        return Ok(());
+    };
+    if policy == RateLimitPolicy::FailFast || wait > MAX_QUEUE_WAIT_SECS {
+        return Err(rate_limited(&bucket.provider, wait));
+    }
+
+    let queue = QUEUES
+        .lock()
+        .map(|mut queues| queues.entry(bucket.clone()).or_default().clone())
+        .map_err(|e| CogniaError::Internal(e.to_string()))?;
+
+    update_status(bucket, |status| status.queued += 1);
+    let _turn = queue.lock().await;
+    // Whoever held the queue before may already have waited out the window
+    if let Some(wait) = rate_limit_status(bucket).and_then(|s| s.wait_secs(now_secs())) {
+        log::info!(
+            "{} {} rate limit exhausted, waiting {}s for reset",
+            bucket.provider,
+            bucket.resource,
+            wait
+        );
+        tokio::time::sleep(std::time::Duration::from_secs(wait)).await;
+    }
+    update_status(bucket, |status| {
+        status.queued = status.queued.saturating_sub(1)
+    });
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ConditionalEntry {
+    etag: String,
+    body: serde_json::Value,
+}
+
+/// Rate-limited GET client that revalidates JSON bodies with `If-None-Match`
+/// and serves 304 responses from the metadata cache
+#[derive(Debug, Clone)]
+pub struct ConditionalClient {
+    provider: String,
+    cache_dir: Option<PathBuf>,
+    policy: RateLimitPolicy,
+    token: Option<String>,
+}
+
+impl ConditionalClient {
+    pub fn new(provider: &str) -> Self {
+        Self {
+            provider: provider.to_string(),
+            cache_dir: None,
+            policy: RateLimitPolicy::default(),
+            token: None,
+        }
+    }
+
+    /// Token the requests are authenticated with; quotas and cached bodies
+    /// are kept apart per token
+    pub fn with_token(mut self, token: Option<&str>) -> Self {
+        self.token = token.map(String::from);
+        self
+    }
+
+    /// Quota bucket requests to `url` are charged to
+    pub fn bucket(&self, url: &str) -> RateLimitBucket {
+        RateLimitBucket::new(
+            &self.provider,
+            resource_for_url(url),
+            url_host(url),
+            self.token.as_deref(),
+        )
+    }
+
+    /// Keep ETags and bodies in the metadata cache under `cache_dir`
+    pub fn with_cache_dir(mut self, cache_dir: Option<PathBuf>) -> Self {
+        self.cache_dir = cache_dir;
+        self
+    }
+
+    pub fn with_policy(mut self, policy: RateLimitPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn policy(&self) -> RateLimitPolicy {
+        self.policy
+    }
+
+    fn cache_key(&self, url: &str) -> String {
+        format!(
+            "{}:etag:{}:{}",
+            self.provider,
+            self.bucket(url).account,
+            url
+        )
+    }
+
+    async fn open_cache(cache_dir: &Path) -> Option<MetadataCache> {
+        MetadataCache::open_with_ttl(cache_dir, CONDITIONAL_CACHE_TTL_SECS)
+            .await
+            .ok()
+    }
+
+    /// Queue behind the quota, then send a GET with the cached ETag
+    pub async fn send(
+        &self,
+        client: &HttpClient,
+        url: &str,
+        options: RequestOptions,
+        etag: Option<&str>,
+    ) -> CogniaResult<reqwest::Response> {
+        let bucket = self.bucket(url);
+        acquire(&bucket, self.policy).await?;
+
+        let options = match etag {
+            Some(etag) => options.with_header("If-None-Match", etag),
+            None => options,
+        };
+        match client.get_with_options(url, Some(options)).await {
+            Ok(resp) => {
+                record_headers(&bucket, resp.headers());
+                Ok(resp)
+            }
+            Err(NetworkError::RateLimited(retry_after)) => {
+                record_exhausted(&bucket, retry_after);
+                Err(rate_limited(&self.provider, retry_after))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// GET `url` as JSON, answering from the cache when the server says the
+    /// body has not changed
+    pub async fn get_json<T: DeserializeOwned>(
+        &self,
+        client: &HttpClient,
+        url: &str,
+        options: RequestOptions,
+    ) -> CogniaResult<ApiResponse<T>> {
+        let mut cache = match &self.cache_dir {
+            Some(dir) => Self::open_cache(dir).await,
+            None => None,
+        };
+        let key = self.cache_key(url);
+        let cached = match cache.as_mut() {
+            Some(cache) => cache
+                .get::<ConditionalEntry>(&key)
+                .await
+                .ok()
+                .flatten()
+                .map(|c| c.data),
+            None => None,
+        };
+
+        let resp = self
+            .send(
+                client,
+                url,
+                options,
+                cached.as_ref().map(|c| c.etag.as_str()),
+            )
+            .await?;
+
+        if resp.status() == StatusCode::NOT_MODIFIED {
+            let cached = cached.ok_or_else(|| {
+                CogniaError::Network(format!("Unexpected 304 response for {}", url))
+            })?;
+            update_status(&self.bucket(url), |status| status.conditional_hits += 1);
+            let data = serde_json::from_value(cached.body)
+                .map_err(|e| CogniaError::Parse(e.to_string()))?;
+            return Ok(ApiResponse {
+                data,
+                from_cache: true,
+            });
+        }
+
+        let etag = resp
+            .headers()
+            .get("etag")
+            .and_then(|v| v.to_str().ok())
+            .map(String::from);
+        let body: serde_json::Value = resp
+            .json()
+            .await
+            .map_err(|e| CogniaError::Network(e.to_string()))?;
+        let data =
+            serde_json::from_value(body.clone()).map_err(|e| CogniaError::Parse(e.to_string()))?;
+
+        if let (Some(cache), Some(etag)) = (cache.as_mut(), etag) {
+            let entry = ConditionalEntry { etag, body };
+            if let Err(e) = cache
+                .set_with_ttl(&key, &entry, CONDITIONAL_CACHE_TTL_SECS)
+                .await
+            {
+                log::debug!("Failed to cache {} response: {}", self.provider, e);
+            }
+        }
+
+        Ok(ApiResponse {
+            data,
+            from_cache: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderName, HeaderValue};
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(
+                HeaderName::from_static(name),
+                HeaderValue::from_static(value),
+            );
+        }
+        map
+    }
+
+    #[test]
+    fn test_parse_github_headers() {
+        let parsed = parse_rate_limit_headers(&headers(&[
+            ("x-ratelimit-limit", "60"),
+            ("x-ratelimit-remaining", "0"),
+            ("x-ratelimit-used", "60"),
+            ("x-ratelimit-reset", "1700000000"),
+            ("x-ratelimit-resource", "core"),
+        ]))
+        .unwrap();
+
+        assert_eq!(parsed.limit, Some(60));
+        assert_eq!(parsed.remaining, Some(0));
+        assert_eq!(parsed.used, Some(60));
+        assert_eq!(parsed.reset_at, Some(1_700_000_000));
+        assert_eq!(parsed.resource.as_deref(), Some("core"));
+    }
+
+    #[test]
+    fn test_parse_gitlab_headers() {
+        let parsed = parse_rate_limit_headers(&headers(&[
+            ("ratelimit-limit", "2000"),
+            ("ratelimit-remaining", "1999"),
+            ("ratelimit-observed", "1"),
+            ("ratelimit-reset", "1700000060"),
+        ]))
+        .unwrap();
+
+        assert_eq!(parsed.limit, Some(2000));
+        assert_eq!(parsed.remaining, Some(1999));
+        assert_eq!(parsed.used, Some(1));
+        assert!(parse_rate_limit_headers(&headers(&[("etag", "\"abc\"")])).is_none());
+    }
+
+    #[test]
+    fn test_wait_secs_only_when_exhausted() {
+        let mut status = RateLimitStatus {
+            remaining: Some(0),
+            reset_at: Some(1_000),
+            ..RateLimitStatus::default()
+        };
+        assert_eq!(status.wait_secs(900), Some(100));
+        assert_eq!(status.wait_secs(1_000), None);
+
+        status.remaining = Some(5);
+        assert_eq!(status.wait_secs(900), None);
+    }
+
+    #[test]
+    fn test_resource_for_url() {
+        assert_eq!(
+            resource_for_url("https://api.github.com/repos/o/r/releases"),
+            "core"
+        );
+        assert_eq!(
+            resource_for_url("https://api.github.com/search/repositories?q=x"),
+            "search"
+        );
+        assert_eq!(
+            resource_for_url("https://api.github.com/search/code?q=x"),
+            "code_search"
+        );
+        assert_eq!(
+            resource_for_url("https://api.github.com/graphql"),
+            "graphql"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_acquire_fail_fast_and_queue_limit() {
+        let bucket = RateLimitBucket::new("rate-limit-test-exhausted", "core", "example.com", None);
+        update_status(&bucket, |status| {
+            status.remaining = Some(0);
+            status.reset_at = Some(now_secs() + MAX_QUEUE_WAIT_SECS + 60);
+        });
+
+        let err = acquire(&bucket, RateLimitPolicy::FailFast)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, CogniaError::RateLimited { ref provider, .. } if provider == "rate-limit-test-exhausted")
+        );
+        // Too long to queue for, so the queue fails as well
+        assert!(acquire(&bucket, RateLimitPolicy::Queue).await.is_err());
+
+        update_status(&bucket, |status| status.remaining = Some(10));
+        assert!(acquire(&bucket, RateLimitPolicy::FailFast).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_exhaustion_is_scoped_to_token_and_resource() {
+        let provider = "rate-limit-test-scoped";
+        let anonymous_search = RateLimitBucket::new(provider, "search", "example.com", None);
+        update_status(&anonymous_search, |status| {
+            status.remaining = Some(0);
+            status.reset_at = Some(now_secs() + MAX_QUEUE_WAIT_SECS + 60);
+        });
+        assert!(acquire(&anonymous_search, RateLimitPolicy::FailFast)
+            .await
+            .is_err());
+
+        let others = [
+            RateLimitBucket::new(provider, "core", "example.com", None),
+            RateLimitBucket::new(provider, "search", "example.com", Some("token-a")),
+            RateLimitBucket::new(provider, "search", "other.example.com", None),
+        ];
+        for bucket in &others {
+            assert_ne!(bucket, &anonymous_search);
+            assert!(acquire(bucket, RateLimitPolicy::FailFast).await.is_ok());
+        }
+        assert_ne!(
+            RateLimitBucket::new(provider, "core", "example.com", Some("token-a")),
+            RateLimitBucket::new(provider, "core", "example.com", Some("token-b"))
+        );
+    }
+
+    #[test]
+    fn test_client_buckets_follow_token() {
+        let url = "https://api.github.com/repos/o/r/releases";
+        let anonymous = ConditionalClient::new("github");
+        let authed = ConditionalClient::new("github").with_token(Some("secret"));
+
+        assert_ne!(anonymous.bucket(url), authed.bucket(url));
+        assert_ne!(anonymous.cache_key(url), authed.cache_key(url));
+        assert!(!authed.bucket(url).account.contains("secret"));
+    }
+
+    #[tokio::test]
+    async fn test_acquire_queue_waits_for_reset() {
+        let bucket = RateLimitBucket::new("rate-limit-test-queue", "core", "example.com", None);
+        update_status(&bucket, |status| {
+            status.remaining = Some(0);
+            status.reset_at = Some(now_secs() + 1);
+        });
+
+        acquire(&bucket, RateLimitPolicy::Queue).await.unwrap();
+        assert_eq!(rate_limit_status(&bucket).unwrap().queued, 0);
+    }
+}
@@ -1,16 +1,19 @@
 use super::api::update_api_client_from_settings;
+use super::custom_env::CustomEnvProvider;
 use super::system::{SystemEnvironmentProvider, SystemEnvironmentType};
 use super::traits::{Capability, EnvironmentProvider, Provider, SystemPackageProvider};
 use super::{
     adoptium, apk, apt, asdf, brew, bun, bundler, cargo, chocolatey, composer, conan, conda, deno,
-    dnf, docker, dotnet, flatpak, fnm, fvm, gem, git, github, gitlab, goenv, luarocks, macports,
-    mise, msvc, msys2, nix, npm, nvm, pacman, phpbrew, pip, pipx, pnpm, podman, poetry, psgallery,
-    pub_dev, pyenv, rbenv, rustup, scoop, sdkman, snap, uv, vcpkg, volta, winget, wsl, xmake, yarn,
-    zig, zypper,
+    dnf, docker, dotnet, flatpak, fnm, fvm, gem, git, github, gitlab, goenv, juliaup, luarocks,
+    macports, mise, msvc, msys2, nix, npm, nvm, pacman, phpbrew, pip, pipx, pixi, pnpm, podman,
+    poetry, psgallery, pub_dev, pyenv, rbenv, rig, rustup, scoop, sdkman, snap, uv, vcpkg, volta,
+    winget, wsl, xmake, yarn, zig, zypper,
 };
 use crate::config::Settings;
+use crate::core::custom_env_type::CUSTOM_PROVIDER_PREFIX;
 use crate::error::CogniaResult;
 use crate::platform::env::{current_platform, Platform};
+use crate::secrets;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -173,7 +176,9 @@ pub async fn with_settings(settings: &Settings) -> CogniaResult<Self> {
             registry.register_system_provider(sdkman_maven);
         }
 
-        let adoptium_provider = Arc::new(adoptium::AdoptiumProvider::new());
+        let adoptium_provider = Arc::new(
+            adoptium::AdoptiumProvider::new().with_cache_dir(Some(settings.get_cache_dir())),
+        );
         if adoptium_provider.supported_platforms().contains(&platform) {
             registry.register_environment_provider(adoptium_provider.clone());
             registry.register_system_provider(adoptium_provider);
@@ -203,12 +208,25 @@ pub async fn with_settings(settings: &Settings) -> CogniaResult<Self> {
             registry.register_system_provider(fvm_provider);
         }
 
+        let juliaup_provider = Arc::new(juliaup::JuliaupProvider::new());
+        if juliaup_provider.supported_platforms().contains(&platform) {
+            registry.register_environment_provider(juliaup_provider.clone());
+            registry.register_system_provider(juliaup_provider);
+        }
+
+        let rig_provider = Arc::new(rig::RigProvider::new());
+        if rig_provider.supported_platforms().contains(&platform) {
+            registry.register_environment_provider(rig_provider.clone());
+            registry.register_system_provider(rig_provider);
+        }
+
         let pub_provider = Arc::new(pub_dev::PubProvider::new());
         if pub_provider.supported_platforms().contains(&platform) {
             registry.register_system_provider(pub_provider);
         }
 
-        let zig_provider = Arc::new(zig::ZigProvider::new());
+        let zig_provider =
+            Arc::new(zig::ZigProvider::new().with_cache_dir(Some(settings.get_cache_dir())));
         if zig_provider.supported_platforms().contains(&platform) {
             registry.register_environment_provider(zig_provider.clone());
             registry.register_system_provider(zig_provider);
@@ -304,6 +322,9 @@ pub async fn with_settings(settings: &Settings) -> CogniaResult<Self> {
         registry.register_environment_provider(Arc::new(SystemEnvironmentProvider::new(
             SystemEnvironmentType::Fortran,
         )));
+        registry.register_environment_provider(Arc::new(SystemEnvironmentProvider::new(
+            SystemEnvironmentType::Cuda,
+        )));
 
         // Git provider (cross-platform, version control tool management)
         let git_provider = Arc::new(git::GitProvider::new());
@@ -311,16 +332,14 @@ pub async fn with_settings(settings: &Settings) -> CogniaResult<Self> {
             registry.register_system_provider(git_provider);
         }
 
-        // GitHub provider with optional token from settings
-        let github_token = settings
-            .providers
-            .get("github")
-            .and_then(|ps| ps.extra.get("token"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .or_else(|| std::env::var("GITHUB_TOKEN").ok());
+        // GitHub provider with optional token from the secret store
+        let github_token = stored_provider_token(settings, "github").await;
         let github_has_token = github_token.is_some();
-        let github_provider = Arc::new(github::GitHubProvider::new().with_token(github_token));
+        let github_provider = Arc::new(
+            github::GitHubProvider::new()
+                .with_token(github_token)
+                .with_cache_dir(Some(settings.get_cache_dir())),
+        );
         registry.register_provider(github_provider);
         registry.api_provider_config.insert(
             "github".into(),
@@ -330,14 +349,8 @@ pub async fn with_settings(settings: &Settings) -> CogniaResult<Self> {
             },
         );
 
-        // GitLab provider with optional token and custom instance URL from settings
-        let gitlab_token = settings
-            .providers
-            .get("gitlab")
-            .and_then(|ps| ps.extra.get("token"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .or_else(|| std::env::var("GITLAB_TOKEN").ok());
+        // GitLab provider with optional stored token and custom instance URL from settings
+        let gitlab_token = stored_provider_token(settings, "gitlab").await;
         let gitlab_has_token = gitlab_token.is_some();
         let gitlab_url = settings
             .providers
@@ -351,7 +364,8 @@ pub async fn with_settings(settings: &Settings) -> CogniaResult<Self> {
         let gitlab_provider = Arc::new(
             gitlab::GitLabProvider::new()
                 .with_token(gitlab_token)
-                .with_instance_url(gitlab_url),
+                .with_instance_url(gitlab_url)
+                .with_cache_dir(Some(settings.get_cache_dir())),
         );
         registry.register_provider(gitlab_provider);
         registry.api_provider_config.insert(
@@ -500,6 +514,12 @@ pub async fn with_settings(settings: &Settings) -> CogniaResult<Self> {
             registry.register_system_provider(pipx_provider);
         }
 
+        // Register pixi provider (conda-compatible project/global environments)
+        let pixi_provider = Arc::new(pixi::PixiProvider::new());
+        if pixi_provider.supported_platforms().contains(&platform) {
+            registry.register_system_provider(pixi_provider);
+        }
+
         let platform = current_platform();
 
         match platform {
@@ -596,6 +616,44 @@ pub fn register_environment_provider<P: EnvironmentProvider + 'static>(
         self.providers.insert(id, provider);
     }
 
+    /// Replace the providers of user-defined environment types
+    pub fn set_custom_environment_providers(&mut self, providers: Vec<Arc<CustomEnvProvider>>) {
+        self.providers
+            .retain(|id, _| !id.starts_with(CUSTOM_PROVIDER_PREFIX));
+        self.environment_providers
+            .retain(|id, _| !id.starts_with(CUSTOM_PROVIDER_PREFIX));
+        for provider in providers {
+            self.register_environment_provider(provider);
+        }
+    }
+
+    /// Carry the user-defined environment type providers over from a
+    /// registry this one replaces
+    pub fn copy_custom_environment_providers(&mut self, other: &ProviderRegistry) {
+        for (id, provider) in &other.environment_providers {
+            if !id.starts_with(CUSTOM_PROVIDER_PREFIX) {
+                continue;
+            }
+            if let Some(base) = other.providers.get(id) {
+                self.providers.insert(id.clone(), base.clone());
+                self.environment_providers
+                    .insert(id.clone(), provider.clone());
+            }
+        }
+    }
+
+    /// Logical env types of the registered user-defined environment types
+    pub fn custom_environment_types(&self) -> Vec<String> {
+        let mut env_types: Vec<String> = self
+            .environment_providers
+            .keys()
+            .filter_map(|id| id.strip_prefix(CUSTOM_PROVIDER_PREFIX))
+            .map(str::to_string)
+            .collect();
+        env_types.sort();
+        env_types
+    }
+
     pub fn get(&self, id: &str) -> Option<Arc<dyn Provider>> {
         self.providers.get(id).cloned()
     }
@@ -698,14 +756,18 @@ pub async fn find_for_package(
     }
 
     pub fn get_provider_info(&self, id: &str) -> Option<ProviderInfo> {
-        self.providers.get(id).map(|p| ProviderInfo {
-            id: p.id().to_string(),
-            display_name: p.display_name().to_string(),
-            capabilities: p.capabilities().into_iter().collect(),
-            platforms: p.supported_platforms(),
-            priority: self.get_effective_priority(id, p.priority()),
-            is_environment_provider: self.environment_providers.contains_key(id),
-            enabled: self.is_provider_enabled(id),
+        self.providers.get(id).map(|p| {
+            let mut capabilities: Vec<Capability> = p.capabilities().into_iter().collect();
+            capabilities.sort_by_key(|c| c.as_str());
+            ProviderInfo {
+                id: p.id().to_string(),
+                display_name: p.display_name().to_string(),
+                capabilities,
+                platforms: p.supported_platforms(),
+                priority: self.get_effective_priority(id, p.priority()),
+                is_environment_provider: self.environment_providers.contains_key(id),
+                enabled: self.is_provider_enabled(id),
+            }
         })
     }
 
@@ -772,6 +834,22 @@ pub fn is_provider_enabled(&self, id: &str) -> bool {
     }
 }
 
+/// Token for an API provider: the secret store, a plaintext settings token
+/// not yet migrated, then the provider's env var
+async fn stored_provider_token(settings: &Settings, provider: &str) -> Option<String> {
+    let stored = crate::platform::secrets::global()
+        .get(
+            secrets::PROVIDER_SECRET_NAMESPACE,
+            &secrets::provider_store_key(provider),
+        )
+        .await
+        .ok()
+        .flatten();
+    secrets::normalized_secret(stored)
+        .or_else(|| secrets::normalized_secret(settings.get_provider_legacy_token(provider)))
+        .or_else(|| secrets::provider_env_var(provider).and_then(|key| std::env::var(key).ok()))
+}
+
 impl Default for ProviderRegistry {
     fn default() -> Self {
         Self::new()
@@ -886,6 +964,33 @@ async fn with_defaults_registers_known_providers_for_platform() {
         }
     }
 
+    #[tokio::test]
+    async fn every_registered_provider_declares_capabilities() {
+        let registry = ProviderRegistry::with_defaults().await.unwrap();
+        let infos = registry.list_all_info();
+        assert!(!infos.is_empty());
+        for info in infos {
+            assert!(
+                !info.capabilities.is_empty(),
+                "provider '{}' declares no capabilities",
+                info.id
+            );
+        }
+
+        // The declared flag must agree with what install actually does
+        for id in registry.list_system_package_provider_ids() {
+            let provider = registry.get_system_provider(&id).unwrap();
+            assert_eq!(
+                provider
+                    .capabilities()
+                    .contains(&Capability::RequiresElevation),
+                provider.requires_elevation("install"),
+                "provider '{}' declares RequiresElevation inconsistently",
+                id
+            );
+        }
+    }
+
     #[tokio::test]
     async fn disabled_providers_remain_visible_in_provider_list() {
         let mut settings = Settings::default();
@@ -0,0 +1,155 @@
+//! Provider tests against recorded registry responses.
+//!
+//! Requests are answered from `tests/fixtures/http` and any request without a
+//! fixture fails the test. Run the tests with `COGNIA_HTTP_REPLAY=record` to
+//! refresh the fixtures from the live endpoints.
+
+use super::cargo::CargoProvider;
+use super::github::GitHubProvider;
+use super::npm::NpmProvider;
+use super::pip::PipProvider;
+use super::selftest::{run_selftest, SELFTEST_PROVIDERS};
+use super::{Provider, ProviderRegistry, SearchOptions, SharedRegistry};
+use crate::platform::http_replay::{
+    default_fixtures_dir, ReplayMode, ReplaySession, REPLAY_MODE_ENV,
+};
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+struct ReplayHarness {
+    registry: SharedRegistry,
+    session: ReplaySession,
+}
+
+impl ReplayHarness {
+    fn new() -> Self {
+        let mode = std::env::var(REPLAY_MODE_ENV)
+            .ok()
+            .and_then(|mode| mode.parse().ok())
+            .unwrap_or(ReplayMode::Replay);
+
+        let mut registry = ProviderRegistry::new();
+        registry.register_provider(Arc::new(NpmProvider::new()));
+        registry.register_provider(Arc::new(PipProvider::new()));
+        registry.register_provider(Arc::new(GitHubProvider::new()));
+        registry.register_provider(Arc::new(CargoProvider::new()));
+
+        Self {
+            registry: Arc::new(RwLock::new(registry)),
+            session: ReplaySession::new(mode, default_fixtures_dir()),
+        }
+    }
+
+    async fn provider(&self, id: &str) -> Arc<dyn Provider> {
+        self.registry
+            .read()
+            .await
+            .get(id)
+            .unwrap_or_else(|| panic!("provider {} is not registered", id))
+    }
+
+    /// Runs `future` against the fixtures, failing on any request that had
+    /// no fixture
+    async fn run<F: Future>(&self, future: F) -> F::Output {
+        let output = self.session.scope(future).await;
+        let misses = self.session.misses();
+        assert!(
+            misses.is_empty(),
+            "requests without a replay fixture: {:?}",
+            misses
+        );
+        output
+    }
+}
+
+#[tokio::test]
+async fn test_npm_search_from_registry() {
+    let harness = ReplayHarness::new();
+    let npm = harness.provider("npm").await;
+    let options = SearchOptions {
+        limit: Some(2),
+        page: None,
+    };
+
+    let results = harness.run(npm.search("react", options)).await.unwrap();
+
+    let names: Vec<&str> = results.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, vec!["react", "react-dom"]);
+    assert_eq!(results[0].latest_version.as_deref(), Some("18.3.1"));
+    assert!(results.iter().all(|p| p.provider == "npm"));
+}
+
+#[tokio::test]
+async fn test_pip_versions_and_info_from_pypi() {
+    let harness = ReplayHarness::new();
+    let pip = harness.provider("pip").await;
+
+    let (versions, info) = harness
+        .run(async {
+            (
+                pip.get_versions("requests").await,
+                pip.get_package_info("requests").await,
+            )
+        })
+        .await;
+
+    let mut versions: Vec<String> = versions.unwrap().into_iter().map(|v| v.version).collect();
+    versions.sort();
+    assert_eq!(versions, vec!["2.31.0", "2.32.2", "2.32.3"]);
+
+    let info = info.unwrap();
+    assert_eq!(info.name, "requests");
+    assert_eq!(info.description.as_deref(), Some("Python HTTP for Humans."));
+    assert_eq!(info.license.as_deref(), Some("Apache-2.0"));
+    assert_eq!(info.versions.len(), 3);
+}
+
+#[tokio::test]
+async fn test_github_release_listing() {
+    let harness = ReplayHarness::new();
+    let github = harness.provider("github").await;
+
+    let versions = harness.run(github.get_versions("cli/cli")).await.unwrap();
+    let tags: Vec<&str> = versions.iter().map(|v| v.version.as_str()).collect();
+    assert_eq!(tags, vec!["2.50.0", "2.49.2", "2.49.1-rc.1"]);
+    assert_eq!(
+        versions[0].release_date.as_deref(),
+        Some("2024-05-29T16:04:45Z")
+    );
+
+    let info = harness
+        .run(github.get_package_info("cli/cli"))
+        .await
+        .unwrap();
+    assert_eq!(info.homepage.as_deref(), Some("https://cli.github.com"));
+    assert_eq!(info.license.as_deref(), Some("MIT"));
+    assert_eq!(info.versions.len(), 3);
+}
+
+#[tokio::test]
+async fn test_cargo_versions_from_crates_io() {
+    let harness = ReplayHarness::new();
+    let cargo = harness.provider("cargo").await;
+
+    let versions = harness.run(cargo.get_versions("serde")).await.unwrap();
+    let versions: Vec<&str> = versions.iter().map(|v| v.version.as_str()).collect();
+    assert_eq!(versions, vec!["1.0.203", "1.0.202"]);
+}
+
+#[tokio::test]
+async fn test_selftest_accepts_recorded_responses() {
+    let harness = ReplayHarness::new();
+
+    for provider in SELFTEST_PROVIDERS {
+        let report = harness.run(run_selftest(provider)).await.unwrap();
+        assert!(report.error.is_none(), "{}: {:?}", provider, report.error);
+        assert!(
+            report.findings.is_empty(),
+            "{}: {:?}",
+            provider,
+            report.findings
+        );
+        assert!(report.compatible);
+    }
+}
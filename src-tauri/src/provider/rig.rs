@@ -0,0 +1,691 @@
+use super::traits::*;
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::{
+    env::{EnvModifications, Platform},
+    process::{self, ProcessOptions},
+};
+use async_trait::async_trait;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const RIG_LINUX_RELEASE_URL: &str = "https://github.com/r-lib/rig/releases/download/latest";
+
+/// An R installation managed by rig, from `rig list`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RigInstall {
+    /// Name as passed to `rig default` / `rig rm` (`4.3.2`, `4.3-arm64`)
+    pub name: String,
+    pub version: String,
+    pub is_default: bool,
+    pub aliases: Vec<String>,
+    pub path: Option<PathBuf>,
+}
+
+/// rig - the R installation manager
+///
+/// Installs R versions side by side and switches the default `R` on PATH.
+/// Installing, removing and switching modify system locations, so they run
+/// under sudo on Linux.
+pub struct RigProvider;
+
+impl RigProvider {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn run_rig(&self, args: &[&str]) -> CogniaResult<String> {
+        let opts = ProcessOptions::new().with_timeout(Duration::from_secs(120));
+        let output = process::execute("rig", args, Some(opts)).await?;
+        if output.success {
+            Ok(output.stdout)
+        } else {
+            Err(CogniaError::Provider(output.stderr))
+        }
+    }
+
+    /// Run a rig command that changes system R installs
+    async fn run_rig_admin(&self, args: &[&str], timeout: Duration) -> CogniaResult<String> {
+        let opts = ProcessOptions::new().with_timeout(timeout);
+        let output = if cfg!(target_os = "linux") {
+            let mut sudo_args = vec!["rig"];
+            sudo_args.extend_from_slice(args);
+            process::execute("sudo", &sudo_args, Some(opts)).await?
+        } else {
+            process::execute("rig", args, Some(opts)).await?
+        };
+        if output.success {
+            Ok(output.stdout)
+        } else {
+            Err(CogniaError::Provider(output.stderr))
+        }
+    }
+
+    /// Installed R versions, from `rig list --json` or the table on older rig
+    pub async fn list_installs(&self) -> CogniaResult<Vec<RigInstall>> {
+        if let Ok(output) = self.run_rig(&["list", "--json"]).await {
+            if let Some(installs) = parse_list_json(&output) {
+                return Ok(installs);
+            }
+        }
+        let output = self.run_rig(&["list"]).await?;
+        Ok(parse_list_text(&output))
+    }
+
+    /// Installed R providing `version`, matched by rig name first
+    async fn install_for(&self, version: &str) -> CogniaResult<RigInstall> {
+        let installs = self.list_installs().await?;
+        installs
+            .iter()
+            .find(|i| i.name == version || i.aliases.iter().any(|a| a == version))
+            .or_else(|| installs.iter().find(|i| i.version == version))
+            .cloned()
+            .ok_or_else(|| CogniaError::VersionNotInstalled(format!("R {}", version)))
+    }
+
+    /// Install rig itself: Homebrew on macOS, winget on Windows and the
+    /// release tarball unpacked into `/usr/local` on Linux
+    pub async fn self_install(&self) -> CogniaResult<String> {
+        let opts = || ProcessOptions::new().with_timeout(Duration::from_secs(600));
+
+        let output = if cfg!(windows) {
+            process::execute(
+                "winget",
+                &[
+                    "install",
+                    "--id",
+                    "posit.rig",
+                    "-e",
+                    "--accept-package-agreements",
+                    "--accept-source-agreements",
+                ],
+                Some(opts()),
+            )
+            .await?
+        } else if cfg!(target_os = "macos") {
+            if process::which("brew").await.is_none() {
+                return Err(CogniaError::Installation(
+                    "Homebrew is required to install rig; see https://github.com/r-lib/rig".into(),
+                ));
+            }
+            let tap = process::execute("brew", &["tap", "r-lib/rig"], Some(opts())).await?;
+            if !tap.success {
+                return Err(CogniaError::Installation(tap.stderr));
+            }
+            process::execute("brew", &["install", "--cask", "rig"], Some(opts())).await?
+        } else {
+            let url = format!(
+                "{}/rig-linux-{}-latest.tar.gz",
+                RIG_LINUX_RELEASE_URL,
+                std::env::consts::ARCH
+            );
+            let client = crate::platform::proxy::get_client_for_provider("rig");
+            let response = client
+                .get(&url)
+                .timeout(Duration::from_secs(120))
+                .send()
+                .await
+                .and_then(|r| r.error_for_status())
+                .map_err(|e| CogniaError::Network(format!("Failed to download rig: {}", e)))?;
+            let archive = response
+                .bytes()
+                .await
+                .map_err(|e| CogniaError::Network(format!("Failed to download rig: {}", e)))?;
+
+            let archive_path =
+                std::env::temp_dir().join(format!("rig-{}.tar.gz", std::process::id()));
+            crate::platform::fs::write_file(&archive_path, &archive).await?;
+            let archive_arg = archive_path.to_string_lossy().into_owned();
+            let result = process::execute(
+                "sudo",
+                &["tar", "xzf", archive_arg.as_str(), "-C", "/usr/local"],
+                Some(opts()),
+            )
+            .await;
+            let _ = crate::platform::fs::remove_file(&archive_path).await;
+            result?
+        };
+
+        if output.success {
+            Ok(output.stdout)
+        } else {
+            Err(CogniaError::Installation(output.stderr))
+        }
+    }
+}
+
+impl Default for RigProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Lenient R version parse: `4.3` -> `4.3.0`, `4.3.2-arm64` -> `4.3.2`
+pub fn parse_r_version(version: &str) -> Option<semver::Version> {
+    let numeric: String = version
+        .trim()
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    let mut parts: Vec<&str> = numeric.split('.').filter(|p| !p.is_empty()).collect();
+    if parts.is_empty() || parts.len() > 3 {
+        return None;
+    }
+    while parts.len() < 3 {
+        parts.push("0");
+    }
+    semver::Version::parse(&parts.join(".")).ok()
+}
+
+/// Whether `version` satisfies a DESCRIPTION constraint such as `>= 4.0.0`
+pub fn constraint_matches(spec: &str, version: &str) -> bool {
+    let Some(version) = parse_r_version(version) else {
+        return false;
+    };
+    let spec: String = spec.split_whitespace().collect();
+    semver::VersionReq::parse(&spec)
+        .map(|req| req.matches(&version))
+        .unwrap_or(false)
+}
+
+pub fn parse_list_json(output: &str) -> Option<Vec<RigInstall>> {
+    let entries: Vec<serde_json::Value> = serde_json::from_str(output).ok()?;
+    Some(
+        entries
+            .iter()
+            .filter_map(|entry| {
+                let name = entry.get("name")?.as_str()?.to_string();
+                let version = entry
+                    .get("version")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or(&name)
+                    .to_string();
+                Some(RigInstall {
+                    version,
+                    is_default: entry
+                        .get("default")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false),
+                    aliases: entry
+                        .get("aliases")
+                        .and_then(|v| v.as_array())
+                        .map(|a| {
+                            a.iter()
+                                .filter_map(|v| v.as_str().map(str::to_string))
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                    path: entry
+                        .get("path")
+                        .and_then(|v| v.as_str())
+                        .map(PathBuf::from),
+                    name,
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Parse the `rig list` table:
+///
+/// ```text
+/// * name   version  aliases
+/// ------------------------------------------
+///   4.2.3  (R 4.2.3)
+/// * 4.3.2  (R 4.3.2)  release
+/// ```
+pub fn parse_list_text(output: &str) -> Vec<RigInstall> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.chars().all(|c| c == '-'))
+        .filter_map(|line| {
+            let (is_default, rest) = match line.strip_prefix('*') {
+                Some(rest) => (true, rest.trim_start()),
+                None => (false, line),
+            };
+            let name = rest.split_whitespace().next()?;
+            if name == "name" {
+                return None;
+            }
+            let mut version = name.to_string();
+            let mut aliases = Vec::new();
+            let mut tail = rest[name.len()..].trim();
+            if let Some(inner) = tail.strip_prefix("(R ") {
+                if let Some((v, after)) = inner.split_once(')') {
+                    version = v.trim().to_string();
+                    tail = after.trim();
+                }
+            }
+            aliases.extend(tail.split_whitespace().map(str::to_string));
+            Some(RigInstall {
+                name: name.to_string(),
+                version,
+                is_default,
+                aliases,
+                path: None,
+            })
+        })
+        .collect()
+}
+
+/// Released R versions from `rig available --json`, newest first
+pub fn parse_available_json(output: &str) -> Option<Vec<VersionInfo>> {
+    let entries: Vec<serde_json::Value> = serde_json::from_str(output).ok()?;
+    let mut versions: Vec<(semver::Version, VersionInfo)> = entries
+        .iter()
+        .filter(|entry| {
+            // `devel` and `next` are moving snapshots, not installable pins
+            !matches!(
+                entry.get("type").and_then(|v| v.as_str()),
+                Some("devel" | "next")
+            )
+        })
+        .filter_map(|entry| {
+            let version = entry
+                .get("version")
+                .or_else(|| entry.get("name"))
+                .and_then(|v| v.as_str())?;
+            let parsed = parse_r_version(version)?;
+            Some((
+                parsed,
+                VersionInfo {
+                    version: version.to_string(),
+                    release_date: entry
+                        .get("date")
+                        .and_then(|v| v.as_str())
+                        .map(str::to_string),
+                    deprecated: false,
+                    yanked: false,
+                },
+            ))
+        })
+        .collect();
+    versions.sort_by(|a, b| b.0.cmp(&a.0));
+    versions.dedup_by(|a, b| a.0 == b.0);
+    Some(versions.into_iter().map(|(_, info)| info).collect())
+}
+
+/// Where rig installs R `version` on this platform
+fn default_install_dir(version: &str) -> PathBuf {
+    if cfg!(windows) {
+        PathBuf::from(r"C:\Program Files\R").join(format!("R-{}", version))
+    } else if cfg!(target_os = "macos") {
+        // The framework keeps one directory per minor version
+        let minor = parse_r_version(version)
+            .map(|v| format!("{}.{}", v.major, v.minor))
+            .unwrap_or_else(|| version.to_string());
+        let name = if cfg!(target_arch = "aarch64") {
+            format!("{}-arm64", minor)
+        } else {
+            minor
+        };
+        PathBuf::from("/Library/Frameworks/R.framework/Versions")
+            .join(name)
+            .join("Resources")
+    } else {
+        PathBuf::from("/opt/R").join(version)
+    }
+}
+
+#[async_trait]
+impl Provider for RigProvider {
+    fn id(&self) -> &str {
+        "rig"
+    }
+
+    fn display_name(&self) -> &str {
+        "rig (R Installation Manager)"
+    }
+
+    fn capabilities(&self) -> HashSet<Capability> {
+        let mut capabilities = HashSet::from([
+            Capability::Install,
+            Capability::Uninstall,
+            Capability::Search,
+            Capability::List,
+            Capability::VersionSwitch,
+            Capability::MultiVersion,
+            Capability::Info,
+            Capability::Versions,
+        ]);
+        if !cfg!(windows) {
+            capabilities.insert(Capability::RequiresElevation);
+        }
+        capabilities
+    }
+
+    fn supported_platforms(&self) -> Vec<Platform> {
+        vec![Platform::Windows, Platform::MacOS, Platform::Linux]
+    }
+
+    fn priority(&self) -> i32 {
+        85
+    }
+
+    async fn is_available(&self) -> bool {
+        if process::which("rig").await.is_none() {
+            return false;
+        }
+        match process::execute("rig", &["--version"], None).await {
+            Ok(output) => output.success,
+            Err(_) => false,
+        }
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        _options: SearchOptions,
+    ) -> CogniaResult<Vec<PackageSummary>> {
+        let versions = self.get_versions("r").await?;
+        Ok(versions
+            .into_iter()
+            .filter(|v| query.is_empty() || v.version.contains(query))
+            .take(20)
+            .map(|v| PackageSummary {
+                name: format!("r@{}", v.version),
+                description: Some("R language for statistical computing".into()),
+                latest_version: Some(v.version),
+                provider: self.id().to_string(),
+            })
+            .collect())
+    }
+
+    async fn get_package_info(&self, name: &str) -> CogniaResult<PackageInfo> {
+        let version = name.strip_prefix("r@").unwrap_or(name);
+
+        Ok(PackageInfo {
+            name: name.into(),
+            display_name: Some(format!("R {}", version)),
+            description: Some("R language for statistical computing".into()),
+            homepage: Some("https://www.r-project.org".into()),
+            license: Some("GPL-2.0-or-later".into()),
+            repository: Some("https://github.com/wch/r-source".into()),
+            versions: vec![VersionInfo {
+                version: version.to_string(),
+                release_date: None,
+                deprecated: false,
+                yanked: false,
+            }],
+            provider: self.id().into(),
+        })
+    }
+
+    async fn get_versions(&self, _name: &str) -> CogniaResult<Vec<VersionInfo>> {
+        let output = self.run_rig(&["available", "--json"]).await?;
+        parse_available_json(&output)
+            .ok_or_else(|| CogniaError::Parse("Failed to parse rig available output".into()))
+    }
+
+    async fn install(&self, req: InstallRequest) -> CogniaResult<InstallReceipt> {
+        let version = req.version.as_deref().unwrap_or("release");
+
+        self.run_rig_admin(&["add", version], Duration::from_secs(1800))
+            .await?;
+
+        let install = self.install_for(version).await?;
+        Ok(InstallReceipt {
+            name: "r".to_string(),
+            install_path: install
+                .path
+                .clone()
+                .unwrap_or_else(|| default_install_dir(&install.name)),
+            version: install.version,
+            provider: self.id().to_string(),
+            files: vec![],
+            installed_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    async fn uninstall(&self, req: UninstallRequest) -> CogniaResult<()> {
+        let version = req
+            .version
+            .ok_or_else(|| CogniaError::Provider("Version required for uninstall".into()))?;
+
+        let install = self.install_for(&version).await?;
+        self.run_rig_admin(&["rm", &install.name], Duration::from_secs(300))
+            .await?;
+        Ok(())
+    }
+
+    async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<InstalledPackage>> {
+        Ok(self
+            .list_installed_versions()
+            .await?
+            .into_iter()
+            .map(|v| InstalledPackage {
+                name: format!("r@{}", v.version),
+                version: v.version,
+                provider: self.id().into(),
+                install_path: v.install_path,
+                installed_at: String::new(),
+                is_global: true,
+                kind: None,
+            })
+            .filter(|p| match filter.name_filter {
+                Some(ref name_filter) => p.name.contains(name_filter),
+                None => true,
+            })
+            .collect())
+    }
+
+    async fn check_updates(&self, _packages: &[String]) -> CogniaResult<Vec<UpdateInfo>> {
+        let Some(current) = self.get_current_version().await? else {
+            return Ok(vec![]);
+        };
+        let latest = self.get_versions("r").await.unwrap_or_default();
+        match (latest.first(), parse_r_version(&current)) {
+            (Some(latest), Some(current_parsed))
+                if parse_r_version(&latest.version).is_some_and(|l| l > current_parsed) =>
+            {
+                Ok(vec![UpdateInfo {
+                    name: "r".into(),
+                    current_version: current,
+                    latest_version: latest.version.clone(),
+                    provider: self.id().into(),
+                }])
+            }
+            _ => Ok(vec![]),
+        }
+    }
+}
+
+#[async_trait]
+impl EnvironmentProvider for RigProvider {
+    async fn list_installed_versions(&self) -> CogniaResult<Vec<InstalledVersion>> {
+        Ok(self
+            .list_installs()
+            .await?
+            .into_iter()
+            .map(|install| InstalledVersion {
+                install_path: install
+                    .path
+                    .clone()
+                    .unwrap_or_else(|| default_install_dir(&install.name)),
+                version: install.version,
+                size: None,
+                installed_at: None,
+                is_current: install.is_default,
+            })
+            .collect())
+    }
+
+    async fn get_current_version(&self) -> CogniaResult<Option<String>> {
+        Ok(self
+            .list_installs()
+            .await?
+            .into_iter()
+            .find(|i| i.is_default)
+            .map(|i| i.version))
+    }
+
+    async fn set_global_version(&self, version: &str) -> CogniaResult<()> {
+        let install = self.install_for(version).await?;
+        self.run_rig_admin(&["default", &install.name], Duration::from_secs(120))
+            .await?;
+        Ok(())
+    }
+
+    async fn set_local_version(&self, project_path: &Path, version: &str) -> CogniaResult<()> {
+        let version_file = project_path.join(self.version_file_name());
+        crate::platform::fs::write_file_string(&version_file, version).await?;
+        Ok(())
+    }
+
+    async fn detect_version(&self, start_path: &Path) -> CogniaResult<Option<VersionDetection>> {
+        let mut current = start_path.to_path_buf();
+        loop {
+            let version_file = current.join(".Rversion");
+            if version_file.exists() {
+                if let Ok(content) = crate::platform::fs::read_file_string(&version_file).await {
+                    let version = content.trim().to_string();
+                    if !version.is_empty() {
+                        return Ok(Some(VersionDetection {
+                            version,
+                            source: VersionSource::LocalFile,
+                            source_path: Some(version_file),
+                        }));
+                    }
+                }
+            }
+
+            if !current.pop() {
+                break;
+            }
+        }
+
+        if let Some(version) = self.get_current_version().await? {
+            return Ok(Some(VersionDetection {
+                version,
+                source: VersionSource::SystemDefault,
+                source_path: None,
+            }));
+        }
+
+        Ok(None)
+    }
+
+    fn get_env_modifications(&self, version: &str) -> CogniaResult<EnvModifications> {
+        Ok(EnvModifications::new().prepend_path(default_install_dir(version).join("bin")))
+    }
+
+    fn version_file_name(&self) -> &str {
+        ".Rversion"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[async_trait]
+impl SystemPackageProvider for RigProvider {
+    async fn check_system_requirements(&self) -> CogniaResult<bool> {
+        Ok(self.is_available().await)
+    }
+
+    fn requires_elevation(&self, operation: &str) -> bool {
+        !cfg!(windows) && matches!(operation, "install" | "uninstall" | "switch")
+    }
+
+    async fn get_version(&self) -> CogniaResult<String> {
+        let output = self.run_rig(&["--version"]).await?;
+        Ok(output
+            .split_whitespace()
+            .last()
+            .unwrap_or_default()
+            .to_string())
+    }
+
+    async fn get_executable_path(&self) -> CogniaResult<PathBuf> {
+        process::which("rig")
+            .await
+            .map(PathBuf::from)
+            .ok_or_else(|| CogniaError::Provider("rig not found in PATH".into()))
+    }
+
+    fn get_install_instructions(&self) -> Option<String> {
+        if cfg!(windows) {
+            Some("winget install posit.rig".into())
+        } else if cfg!(target_os = "macos") {
+            Some("brew tap r-lib/rig && brew install --cask rig".into())
+        } else {
+            Some(format!(
+                "curl -Ls {}/rig-linux-$(arch)-latest.tar.gz | sudo tar xz -C /usr/local",
+                RIG_LINUX_RELEASE_URL
+            ))
+        }
+    }
+
+    async fn is_package_installed(&self, name: &str) -> CogniaResult<bool> {
+        let version = name.strip_prefix("r@").unwrap_or(name);
+        let versions = self.list_installed_versions().await?;
+        Ok(versions.iter().any(|v| v.version == version))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_list_json() {
+        let output = r#"[
+            {"name":"4.2.3","default":false,"version":"4.2.3","aliases":[],"path":"/opt/R/4.2.3"},
+            {"name":"4.3.2","default":true,"version":"4.3.2","aliases":["release"],"path":"/opt/R/4.3.2"}
+        ]"#;
+        let installs = parse_list_json(output).unwrap();
+        assert_eq!(installs.len(), 2);
+        assert!(installs[1].is_default);
+        assert_eq!(installs[1].aliases, vec!["release".to_string()]);
+        assert_eq!(installs[0].path, Some(PathBuf::from("/opt/R/4.2.3")));
+        assert!(parse_list_json("not json").is_none());
+    }
+
+    #[test]
+    fn test_parse_list_text() {
+        let output = "* name   version  aliases\n------------------------------------------\n  4.2.3  (R 4.2.3)\n* 4.3-arm64  (R 4.3.2)  release\n  4.1.0\n";
+        let installs = parse_list_text(output);
+        assert_eq!(installs.len(), 3);
+        assert_eq!(installs[0].version, "4.2.3");
+        assert!(!installs[0].is_default);
+        assert_eq!(installs[1].name, "4.3-arm64");
+        assert_eq!(installs[1].version, "4.3.2");
+        assert!(installs[1].is_default);
+        assert_eq!(installs[1].aliases, vec!["release".to_string()]);
+        assert_eq!(installs[2].version, "4.1.0");
+    }
+
+    #[test]
+    fn test_parse_available_json_sorts_and_skips_devel() {
+        let output = r#"[
+            {"name":"4.2.3","version":"4.2.3","date":"2023-03-15","type":"release"},
+            {"name":"4.4.1","version":"4.4.1","date":"2024-06-14","type":"release"},
+            {"name":"devel","version":"4.5.0","type":"devel"}
+        ]"#;
+        let versions = parse_available_json(output).unwrap();
+        let names: Vec<&str> = versions.iter().map(|v| v.version.as_str()).collect();
+        assert_eq!(names, vec!["4.4.1", "4.2.3"]);
+        assert_eq!(versions[0].release_date.as_deref(), Some("2024-06-14"));
+    }
+
+    #[test]
+    fn test_constraint_matches() {
+        assert!(constraint_matches(">= 4.0.0", "4.3.2"));
+        assert!(constraint_matches(">= 4.0", "4.0.0"));
+        assert!(!constraint_matches(">= 4.1.0", "4.0.5"));
+        assert!(constraint_matches(">= 3.5", "4.3-arm64"));
+        assert!(!constraint_matches(">= 4.0.0", "devel"));
+    }
+
+    #[test]
+    fn test_provider_metadata() {
+        let provider = RigProvider::new();
+        assert_eq!(provider.id(), "rig");
+        assert_eq!(provider.version_file_name(), ".Rversion");
+        assert!(provider.capabilities().contains(&Capability::Install));
+        assert_eq!(provider.requires_elevation("install"), !cfg!(windows));
+        assert!(!provider.requires_elevation("list"));
+    }
+}
@@ -339,6 +339,35 @@ pub async fn set_profile(&self, profile: &str) -> CogniaResult<()> {
         self.run_rustup(&["set", "profile", profile]).await?;
         Ok(())
     }
+
+    /// Disk usage of every installed toolchain and its components
+    pub async fn toolchain_sizes(&self) -> CogniaResult<RustToolchainSizeReport> {
+        let rustup_home = self.rustup_home()?.clone();
+        tokio::task::spawn_blocking(move || scan_toolchain_sizes(&rustup_home))
+            .await
+            .map_err(|e| CogniaError::Internal(format!("Toolchain size scan failed: {}", e)))
+    }
+
+    pub async fn uninstall_toolchain(&self, toolchain: &str) -> CogniaResult<()> {
+        self.run_rustup(&["toolchain", "uninstall", toolchain])
+            .await?;
+        Ok(())
+    }
+
+    /// Delete rustup's download cache. Returns the bytes freed.
+    pub async fn prune_downloads(&self) -> CogniaResult<u64> {
+        let downloads = self.rustup_home()?.join("downloads");
+        if !downloads.is_dir() {
+            return Ok(0);
+        }
+        let scan_path = downloads.clone();
+        let size = tokio::task::spawn_blocking(move || dir_size_blocking(&scan_path))
+            .await
+            .unwrap_or(0);
+        tokio::fs::remove_dir_all(&downloads).await?;
+        tokio::fs::create_dir_all(&downloads).await?;
+        Ok(size)
+    }
 }
 
 impl Default for RustupProvider {
@@ -368,6 +397,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::VersionSwitch,
             Capability::MultiVersion,
             Capability::ProjectLocal,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
 
@@ -529,6 +560,7 @@ async fn list_installed(
                 install_path: rustup_home.join("toolchains").join(&toolchain),
                 installed_at: String::new(),
                 is_global: true,
+                kind: None,
             })
             .collect();
 
@@ -763,6 +795,361 @@ pub struct RustupOverride {
     pub toolchain: String,
 }
 
+/// Broad component groups used for size reporting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RustComponentKind {
+    Rustc,
+    Std,
+    Docs,
+    Other,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RustComponentSize {
+    /// Installed component name, e.g. `rust-std-wasm32-unknown-unknown`
+    pub name: String,
+    pub kind: RustComponentKind,
+    /// Target triple of a `rust-std` component
+    pub target: Option<String>,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RustToolchainSize {
+    /// Directory name under `toolchains/`, e.g. `nightly-2024-01-15-x86_64-unknown-linux-gnu`
+    pub name: String,
+    /// Toolchain name without the host triple, e.g. `nightly-2024-01-15`
+    pub channel: String,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub is_nightly: bool,
+    /// Release date (`YYYY-MM-DD`) from the name or the channel manifest
+    pub date: Option<String>,
+    /// Bytes that could be freed by removing `rust-docs`
+    pub docs_bytes: u64,
+    pub components: Vec<RustComponentSize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RustToolchainSizeReport {
+    pub rustup_home: PathBuf,
+    /// Largest first
+    pub toolchains: Vec<RustToolchainSize>,
+    pub downloads_bytes: u64,
+    pub total_bytes: u64,
+    /// Docs of every toolchain plus the download cache
+    pub reclaimable_bytes: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RustupGcOptions {
+    /// Toolchains to remove the `rust-docs` component from
+    #[serde(default)]
+    pub remove_docs: Vec<String>,
+    /// Uninstall nightlies released more than this many days ago
+    pub nightly_older_than_days: Option<u32>,
+    /// Delete `~/.rustup/downloads`
+    #[serde(default)]
+    pub prune_downloads: bool,
+    /// Project directories checked for toolchain pins, besides the ones
+    /// seen in launches
+    #[serde(default)]
+    pub project_dirs: Vec<String>,
+    /// Only report what would be removed
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RustupGcActionKind {
+    RemoveDocs,
+    UninstallToolchain,
+    PruneDownloads,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RustupGcAction {
+    pub kind: RustupGcActionKind,
+    pub toolchain: Option<String>,
+    pub estimated_bytes: u64,
+    pub reason: String,
+    /// Whether the action ran successfully (always false in dry runs)
+    pub applied: bool,
+    pub error: Option<String>,
+}
+
+/// A toolchain the options selected that was left alone, and why
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RustupGcSkipped {
+    pub toolchain: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RustupGcResult {
+    pub dry_run: bool,
+    pub actions: Vec<RustupGcAction>,
+    pub skipped: Vec<RustupGcSkipped>,
+    pub estimated_savings: u64,
+    pub freed_bytes: u64,
+    pub scanned_projects: Vec<String>,
+}
+
+/// Toolchain pin found in a project directory
+#[derive(Debug, Clone)]
+pub struct RustToolchainPin {
+    pub toolchain: String,
+    pub project_dir: String,
+}
+
+/// Walk `<rustup_home>/toolchains/*` and size each toolchain and the
+/// components recorded in its `lib/rustlib/manifest-*` files. Linked
+/// (symlinked) toolchains are not managed by rustup and are skipped.
+pub fn scan_toolchain_sizes(rustup_home: &Path) -> RustToolchainSizeReport {
+    let mut toolchains = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(rustup_home.join("toolchains")) {
+        for entry in entries.flatten() {
+            if !entry.file_type().is_ok_and(|t| t.is_dir()) {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().to_string();
+            toolchains.push(scan_toolchain(&name, &entry.path()));
+        }
+    }
+    toolchains.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes));
+
+    let downloads_bytes = dir_size_blocking(&rustup_home.join("downloads"));
+    let toolchain_bytes: u64 = toolchains.iter().map(|tc| tc.size_bytes).sum();
+    let docs_bytes: u64 = toolchains.iter().map(|tc| tc.docs_bytes).sum();
+    RustToolchainSizeReport {
+        rustup_home: rustup_home.to_path_buf(),
+        toolchains,
+        downloads_bytes,
+        total_bytes: toolchain_bytes + downloads_bytes,
+        reclaimable_bytes: docs_bytes + downloads_bytes,
+    }
+}
+
+fn scan_toolchain(name: &str, path: &Path) -> RustToolchainSize {
+    let rustlib = path.join("lib").join("rustlib");
+    let installed = std::fs::read_to_string(rustlib.join("components")).unwrap_or_default();
+    let components: Vec<RustComponentSize> = installed
+        .lines()
+        .map(str::trim)
+        .filter(|component| !component.is_empty())
+        .map(|component| {
+            let manifest = std::fs::read_to_string(rustlib.join(format!("manifest-{}", component)))
+                .unwrap_or_default();
+            let (kind, target) = classify_component(component);
+            RustComponentSize {
+                name: component.to_string(),
+                kind,
+                target,
+                size_bytes: component_manifest_size(path, &manifest),
+            }
+        })
+        .collect();
+
+    let host = components
+        .iter()
+        .find(|c| c.kind == RustComponentKind::Rustc)
+        .and_then(|c| c.name.strip_prefix("rustc-"));
+    let channel = host
+        .and_then(|host| name.strip_suffix(&format!("-{}", host)))
+        .unwrap_or(name)
+        .to_string();
+    let date = channel_date(&channel).or_else(|| {
+        std::fs::read_to_string(rustlib.join("multirust-channel-manifest.toml"))
+            .ok()
+            .and_then(|manifest| parse_channel_manifest_date(&manifest))
+    });
+
+    RustToolchainSize {
+        name: name.to_string(),
+        is_nightly: channel.starts_with("nightly"),
+        path: path.to_path_buf(),
+        size_bytes: dir_size_blocking(path),
+        docs_bytes: components
+            .iter()
+            .filter(|c| c.kind == RustComponentKind::Docs)
+            .map(|c| c.size_bytes)
+            .sum(),
+        channel,
+        date,
+        components,
+    }
+}
+
+fn classify_component(name: &str) -> (RustComponentKind, Option<String>) {
+    if let Some(target) = name.strip_prefix("rust-std-") {
+        return (RustComponentKind::Std, Some(target.to_string()));
+    }
+    if name == "rust-docs" || name.starts_with("rust-docs-") {
+        return (RustComponentKind::Docs, None);
+    }
+    match name.strip_prefix("rustc-") {
+        Some(rest) if !rest.starts_with("dev-") && !rest.starts_with("docs-") => {
+            (RustComponentKind::Rustc, None)
+        }
+        _ => (RustComponentKind::Other, None),
+    }
+}
+
+/// Sum the `file:` and `dir:` entries of a rustup component manifest
+fn component_manifest_size(toolchain_dir: &Path, manifest: &str) -> u64 {
+    manifest
+        .lines()
+        .filter_map(|line| {
+            if let Some(file) = line.strip_prefix("file:") {
+                std::fs::symlink_metadata(toolchain_dir.join(file))
+                    .ok()
+                    .map(|meta| meta.len())
+            } else {
+                line.strip_prefix("dir:")
+                    .map(|dir| dir_size_blocking(&toolchain_dir.join(dir)))
+            }
+        })
+        .sum()
+}
+
+/// `nightly-2024-01-15` → `2024-01-15`
+fn channel_date(channel: &str) -> Option<String> {
+    let (_, date) = channel.split_once('-')?;
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .map(|_| date.to_string())
+}
+
+fn parse_channel_manifest_date(manifest: &str) -> Option<String> {
+    manifest.lines().find_map(|line| {
+        let value = line.trim().strip_prefix("date")?.trim_start();
+        let value = value.strip_prefix('=')?.trim().trim_matches('"');
+        chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .ok()
+            .map(|_| value.to_string())
+    })
+}
+
+fn dir_size_blocking(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|meta| meta.len())
+        .sum()
+}
+
+/// Decide what `rustup_gc` removes. Nightlies are kept when they are the
+/// default or active toolchain (`protected`), when a project pins them, or
+/// when their release date is unknown.
+pub fn plan_rustup_gc(
+    report: &RustToolchainSizeReport,
+    options: &RustupGcOptions,
+    pins: &[RustToolchainPin],
+    protected: &[String],
+    today: chrono::NaiveDate,
+) -> (Vec<RustupGcAction>, Vec<RustupGcSkipped>) {
+    let refers_to = |spec: &str, tc: &RustToolchainSize| spec == tc.name || spec == tc.channel;
+    let mut actions = Vec::new();
+    let mut skipped = Vec::new();
+    let mut uninstalled = HashSet::new();
+
+    if let Some(days) = options.nightly_older_than_days {
+        let cutoff = today - chrono::Duration::days(i64::from(days));
+        for tc in report.toolchains.iter().filter(|tc| tc.is_nightly) {
+            let Some(date) = tc
+                .date
+                .as_deref()
+                .and_then(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+            else {
+                skipped.push(RustupGcSkipped {
+                    toolchain: tc.name.clone(),
+                    reason: "Release date unknown".to_string(),
+                });
+                continue;
+            };
+            if date >= cutoff {
+                continue;
+            }
+            if protected.iter().any(|spec| refers_to(spec, tc)) {
+                skipped.push(RustupGcSkipped {
+                    toolchain: tc.name.clone(),
+                    reason: "Default or active toolchain".to_string(),
+                });
+                continue;
+            }
+            if let Some(pin) = pins.iter().find(|pin| refers_to(&pin.toolchain, tc)) {
+                skipped.push(RustupGcSkipped {
+                    toolchain: tc.name.clone(),
+                    reason: format!("Pinned by {}", pin.project_dir),
+                });
+                continue;
+            }
+            uninstalled.insert(tc.name.clone());
+            actions.push(RustupGcAction {
+                kind: RustupGcActionKind::UninstallToolchain,
+                toolchain: Some(tc.name.clone()),
+                estimated_bytes: tc.size_bytes,
+                reason: format!("Nightly from {}, older than {} days", date, days),
+                applied: false,
+                error: None,
+            });
+        }
+    }
+
+    for requested in &options.remove_docs {
+        let Some(tc) = report.toolchains.iter().find(|tc| refers_to(requested, tc)) else {
+            skipped.push(RustupGcSkipped {
+                toolchain: requested.clone(),
+                reason: "Toolchain not installed".to_string(),
+            });
+            continue;
+        };
+        if uninstalled.contains(&tc.name) {
+            continue;
+        }
+        if tc.docs_bytes == 0 {
+            skipped.push(RustupGcSkipped {
+                toolchain: tc.name.clone(),
+                reason: "No docs installed".to_string(),
+            });
+            continue;
+        }
+        actions.push(RustupGcAction {
+            kind: RustupGcActionKind::RemoveDocs,
+            toolchain: Some(tc.name.clone()),
+            estimated_bytes: tc.docs_bytes,
+            reason: "Remove rust-docs component".to_string(),
+            applied: false,
+            error: None,
+        });
+    }
+
+    if options.prune_downloads && report.downloads_bytes > 0 {
+        actions.push(RustupGcAction {
+            kind: RustupGcActionKind::PruneDownloads,
+            toolchain: None,
+            estimated_bytes: report.downloads_bytes,
+            reason: "Clear the rustup download cache".to_string(),
+            applied: false,
+            error: None,
+        });
+    }
+
+    (actions, skipped)
+}
+
 // ── Pure parsing helpers (extracted for testability) ──
 
 /// Parse `rust-version` field from Cargo.toml content.
@@ -1143,4 +1530,202 @@ fn get_env_modifications_includes_required_vars_with_stable_path_order() {
             None => std::env::remove_var("CARGO_HOME"),
         }
     }
+
+    fn write_toolchain(home: &Path, name: &str, host: &str, docs_len: usize) {
+        let dir = home.join("toolchains").join(name);
+        let rustlib = dir.join("lib").join("rustlib");
+        std::fs::create_dir_all(dir.join("bin")).unwrap();
+        std::fs::create_dir_all(dir.join("share").join("doc").join("rust")).unwrap();
+        std::fs::create_dir_all(&rustlib).unwrap();
+        std::fs::write(dir.join("bin").join("rustc"), vec![0u8; 100]).unwrap();
+        std::fs::write(
+            dir.join("share")
+                .join("doc")
+                .join("rust")
+                .join("index.html"),
+            vec![0u8; docs_len],
+        )
+        .unwrap();
+        std::fs::write(
+            rustlib.join("components"),
+            format!("rustc-{host}\nrust-docs-{host}\n"),
+        )
+        .unwrap();
+        std::fs::write(
+            rustlib.join(format!("manifest-rustc-{}", host)),
+            "file:bin/rustc\n",
+        )
+        .unwrap();
+        std::fs::write(
+            rustlib.join(format!("manifest-rust-docs-{}", host)),
+            "dir:share/doc/rust\n",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_scan_toolchain_sizes_reports_components() {
+        let home = tempfile::tempdir().unwrap();
+        let host = "x86_64-unknown-linux-gnu";
+        write_toolchain(home.path(), &format!("stable-{}", host), host, 400);
+        write_toolchain(
+            home.path(),
+            &format!("nightly-2024-01-15-{}", host),
+            host,
+            50,
+        );
+        std::fs::create_dir_all(home.path().join("downloads")).unwrap();
+        std::fs::write(home.path().join("downloads").join("abc"), vec![0u8; 30]).unwrap();
+
+        let report = scan_toolchain_sizes(home.path());
+        assert_eq!(report.toolchains.len(), 2);
+
+        let stable = &report.toolchains[0];
+        assert_eq!(stable.channel, "stable");
+        assert!(!stable.is_nightly);
+        assert_eq!(stable.docs_bytes, 400);
+        // bin + docs + rustlib bookkeeping files
+        assert!(stable.size_bytes > 500);
+        let rustc = stable
+            .components
+            .iter()
+            .find(|c| c.kind == RustComponentKind::Rustc)
+            .unwrap();
+        assert_eq!(rustc.size_bytes, 100);
+
+        let nightly = &report.toolchains[1];
+        assert_eq!(nightly.channel, "nightly-2024-01-15");
+        assert!(nightly.is_nightly);
+        assert_eq!(nightly.date.as_deref(), Some("2024-01-15"));
+
+        assert_eq!(report.downloads_bytes, 30);
+        assert_eq!(report.reclaimable_bytes, 400 + 50 + 30);
+    }
+
+    #[test]
+    fn test_classify_component() {
+        assert_eq!(
+            classify_component("rust-std-wasm32-unknown-unknown"),
+            (
+                RustComponentKind::Std,
+                Some("wasm32-unknown-unknown".to_string())
+            )
+        );
+        assert_eq!(
+            classify_component("rust-docs-x86_64-pc-windows-msvc").0,
+            RustComponentKind::Docs
+        );
+        assert_eq!(
+            classify_component("rustc-x86_64-pc-windows-msvc").0,
+            RustComponentKind::Rustc
+        );
+        assert_eq!(
+            classify_component("rustc-dev-x86_64-pc-windows-msvc").0,
+            RustComponentKind::Other
+        );
+        assert_eq!(classify_component("rust-src").0, RustComponentKind::Other);
+    }
+
+    #[test]
+    fn test_parse_channel_manifest_date() {
+        let manifest = "manifest-version = \"2\"\ndate = \"2024-03-02\"\n[pkg.rustc]\n";
+        assert_eq!(
+            parse_channel_manifest_date(manifest).as_deref(),
+            Some("2024-03-02")
+        );
+        assert_eq!(parse_channel_manifest_date("date = \"soon\""), None);
+    }
+
+    #[test]
+    fn test_plan_rustup_gc_keeps_pinned_and_default_nightlies() {
+        let toolchain = |name: &str, channel: &str, date: Option<&str>| RustToolchainSize {
+            name: name.to_string(),
+            channel: channel.to_string(),
+            path: PathBuf::from(name),
+            size_bytes: 1000,
+            is_nightly: channel.starts_with("nightly"),
+            date: date.map(str::to_string),
+            docs_bytes: 200,
+            components: Vec::new(),
+        };
+        let report = RustToolchainSizeReport {
+            rustup_home: PathBuf::from("/rustup"),
+            toolchains: vec![
+                toolchain("stable-host", "stable", None),
+                toolchain(
+                    "nightly-2023-01-01-host",
+                    "nightly-2023-01-01",
+                    Some("2023-01-01"),
+                ),
+                toolchain(
+                    "nightly-2023-02-01-host",
+                    "nightly-2023-02-01",
+                    Some("2023-02-01"),
+                ),
+                toolchain(
+                    "nightly-2023-03-01-host",
+                    "nightly-2023-03-01",
+                    Some("2023-03-01"),
+                ),
+                toolchain(
+                    "nightly-2024-05-20-host",
+                    "nightly-2024-05-20",
+                    Some("2024-05-20"),
+                ),
+                toolchain("nightly-host", "nightly", None),
+            ],
+            downloads_bytes: 50,
+            total_bytes: 0,
+            reclaimable_bytes: 0,
+        };
+        let options = RustupGcOptions {
+            remove_docs: vec!["stable".to_string(), "nightly-2023-01-01".to_string()],
+            nightly_older_than_days: Some(30),
+            prune_downloads: true,
+            ..Default::default()
+        };
+        let pins = vec![RustToolchainPin {
+            toolchain: "nightly-2023-02-01".to_string(),
+            project_dir: "/work/app".to_string(),
+        }];
+        let protected = vec!["nightly-2023-03-01-host".to_string()];
+        let today = chrono::NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+
+        let (actions, skipped) = plan_rustup_gc(&report, &options, &pins, &protected, today);
+
+        let uninstalls: Vec<&str> = actions
+            .iter()
+            .filter(|a| a.kind == RustupGcActionKind::UninstallToolchain)
+            .filter_map(|a| a.toolchain.as_deref())
+            .collect();
+        assert_eq!(uninstalls, vec!["nightly-2023-01-01-host"]);
+
+        // Docs removal is dropped for a toolchain that is uninstalled anyway
+        let docs: Vec<&str> = actions
+            .iter()
+            .filter(|a| a.kind == RustupGcActionKind::RemoveDocs)
+            .filter_map(|a| a.toolchain.as_deref())
+            .collect();
+        assert_eq!(docs, vec!["stable-host"]);
+        assert!(actions
+            .iter()
+            .any(|a| a.kind == RustupGcActionKind::PruneDownloads && a.estimated_bytes == 50));
+
+        let reason = |name: &str| {
+            skipped
+                .iter()
+                .find(|s| s.toolchain == name)
+                .map(|s| s.reason.as_str())
+        };
+        assert_eq!(
+            reason("nightly-2023-02-01-host"),
+            Some("Pinned by /work/app")
+        );
+        assert_eq!(
+            reason("nightly-2023-03-01-host"),
+            Some("Default or active toolchain")
+        );
+        assert_eq!(reason("nightly-host"), Some("Release date unknown"));
+        assert_eq!(reason("nightly-2024-05-20-host"), None);
+    }
 }
@@ -2,12 +2,41 @@
 use crate::error::{CogniaError, CogniaResult};
 use crate::platform::{env::Platform, process};
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::HashSet;
 use std::path::PathBuf;
 use std::time::Duration;
 
 pub struct ScoopProvider;
 
+/// A bucket entry from `scoop export` or `scoop bucket list`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoopBucket {
+    pub name: String,
+    pub source: String,
+    pub updated: Option<String>,
+    pub manifests: Option<u64>,
+}
+
+/// An installed app from `scoop export`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ScoopApp {
+    name: String,
+    version: String,
+    global: bool,
+    held: bool,
+}
+
+/// Machine-readable state from `scoop export`. Its JSON keys are fixed,
+/// unlike the column headers of `scoop list`, which follow the console locale.
+#[derive(Debug, Default)]
+struct ScoopExport {
+    buckets: Vec<ScoopBucket>,
+    apps: Vec<ScoopApp>,
+}
+
 impl ScoopProvider {
     pub fn new() -> Self {
         Self
@@ -52,6 +81,188 @@ async fn query_installed_version(&self, name: &str) -> CogniaResult<String> {
             .ok_or_else(|| CogniaError::Provider(format!("Version not found for {}", name)))
     }
 
+    /// Read buckets and installed apps via `scoop export`.
+    async fn export(&self) -> CogniaResult<ScoopExport> {
+        let out = self.run_scoop(&["export"]).await?;
+        Self::parse_export(&out).ok_or_else(|| {
+            CogniaError::Parse("scoop export did not return JSON; Scoop may be outdated".into())
+        })
+    }
+
+    /// Parse `scoop export` JSON. Returns `None` for the plain-text export of
+    /// old Scoop releases so callers can fall back to the table parsers.
+    fn parse_export(output: &str) -> Option<ScoopExport> {
+        let json: Value =
+            serde_json::from_str(output.trim_start_matches('\u{feff}').trim()).ok()?;
+        let entries = |key: &str| {
+            json.get(key)
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default()
+        };
+
+        let buckets = entries("buckets")
+            .iter()
+            .filter_map(|bucket| {
+                Some(ScoopBucket {
+                    name: Self::export_text(bucket.get("Name")?)?,
+                    source: bucket
+                        .get("Source")
+                        .and_then(Self::export_text)
+                        .unwrap_or_default(),
+                    updated: bucket.get("Updated").and_then(Self::export_text),
+                    manifests: bucket.get("Manifests").and_then(|m| {
+                        m.as_u64()
+                            .or_else(|| Self::export_text(m).and_then(|t| t.parse().ok()))
+                    }),
+                })
+            })
+            .collect();
+
+        let apps = entries("apps")
+            .iter()
+            .filter_map(|app| {
+                let info = app
+                    .get("Info")
+                    .and_then(Self::export_text)
+                    .unwrap_or_default();
+                Some(ScoopApp {
+                    name: Self::export_text(app.get("Name")?)?,
+                    version: app
+                        .get("Version")
+                        .and_then(Self::export_text)
+                        .unwrap_or_default(),
+                    global: info.contains("Global install"),
+                    held: info.contains("Held package"),
+                })
+            })
+            .collect();
+
+        Some(ScoopExport { buckets, apps })
+    }
+
+    /// Flatten an export field to text. PowerShell serializes dates either as
+    /// strings or as objects carrying a `value`/`DateTime` member.
+    fn export_text(value: &Value) -> Option<String> {
+        let text = match value {
+            Value::String(s) => s.trim().to_string(),
+            Value::Number(n) => n.to_string(),
+            Value::Object(map) => {
+                return ["value", "DateTime"]
+                    .iter()
+                    .find_map(|key| map.get(*key).and_then(Self::export_text))
+            }
+            _ => return None,
+        };
+        (!text.is_empty()).then_some(text)
+    }
+
+    /// Parse `scoop bucket list` output (tabular: Name Source Updated Manifests).
+    fn parse_bucket_list(output: &str) -> Vec<ScoopBucket> {
+        let mut results = Vec::new();
+        let mut separator_seen = false;
+
+        for line in output.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            if !separator_seen {
+                if trimmed.chars().all(|c| c == '-' || c == ' ') && trimmed.contains('-') {
+                    separator_seen = true;
+                }
+                continue;
+            }
+
+            let parts: Vec<&str> = trimmed.split_whitespace().collect();
+            let Some(name) = parts.first() else {
+                continue;
+            };
+            let manifests = parts
+                .last()
+                .filter(|_| parts.len() > 2)
+                .and_then(|m| m.parse().ok());
+            let updated_end = if manifests.is_some() {
+                parts.len() - 1
+            } else {
+                parts.len()
+            };
+            let updated = parts.get(2..updated_end).map(|p| p.join(" "));
+            results.push(ScoopBucket {
+                name: name.to_string(),
+                source: parts.get(1).unwrap_or(&"").to_string(),
+                updated: updated.filter(|u| !u.is_empty()),
+                manifests,
+            });
+        }
+
+        results
+    }
+
+    // ── Bucket management ──────────────────────────────────────────────
+
+    /// List known buckets, preferring `scoop export` over the table output.
+    pub async fn bucket_list(&self) -> CogniaResult<Vec<ScoopBucket>> {
+        if let Ok(export) = self.export().await {
+            if !export.buckets.is_empty() {
+                return Ok(export.buckets);
+            }
+        }
+        let out = self.run_scoop(&["bucket", "list"]).await?;
+        Ok(Self::parse_bucket_list(&out))
+    }
+
+    /// Add a bucket. Known buckets (`extras`, `versions`, ...) need no repo URL.
+    pub async fn bucket_add(&self, name: &str, repo: Option<&str>) -> CogniaResult<()> {
+        let mut args = vec!["bucket", "add", name];
+        if let Some(repo) = repo {
+            args.push(repo);
+        }
+        self.run_scoop(&args).await?;
+        Ok(())
+    }
+
+    /// Remove a bucket.
+    pub async fn bucket_remove(&self, name: &str) -> CogniaResult<()> {
+        self.run_scoop(&["bucket", "rm", name]).await?;
+        Ok(())
+    }
+
+    // ── Maintenance ────────────────────────────────────────────────────
+
+    /// Remove old versions of `app`, or of every app when `None`.
+    pub async fn cleanup(&self, app: Option<&str>, cache: bool, global: bool) -> CogniaResult<()> {
+        let mut args = vec!["cleanup", app.unwrap_or("*")];
+        if cache {
+            args.push("--cache");
+        }
+        if global {
+            args.push("--global");
+        }
+        self.run_scoop(&args).await?;
+        Ok(())
+    }
+
+    /// Hold an app so `scoop update` skips it.
+    pub async fn hold(&self, name: &str, global: bool) -> CogniaResult<()> {
+        let mut args = vec!["hold", name];
+        if global {
+            args.push("--global");
+        }
+        self.run_scoop(&args).await?;
+        Ok(())
+    }
+
+    /// Release a held app.
+    pub async fn unhold(&self, name: &str, global: bool) -> CogniaResult<()> {
+        let mut args = vec!["unhold", name];
+        if global {
+            args.push("--global");
+        }
+        self.run_scoop(&args).await?;
+        Ok(())
+    }
+
     fn get_scoop_dir() -> Option<PathBuf> {
         std::env::var("SCOOP").ok().map(PathBuf::from).or_else(|| {
             std::env::var("USERPROFILE")
@@ -60,6 +271,18 @@ fn get_scoop_dir() -> Option<PathBuf> {
         })
     }
 
+    fn get_scoop_global_dir() -> PathBuf {
+        std::env::var("SCOOP_GLOBAL")
+            .ok()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| {
+                std::env::var("ProgramData")
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|_| PathBuf::from("C:\\ProgramData"))
+                    .join("scoop")
+            })
+    }
+
     /// Parse scoop search output, handling both old format ('name' (version) [bucket])
     /// and newer tabular format (Name  Version  Source  Binaries).
     fn parse_search_output(output: &str) -> Vec<(String, Option<String>)> {
@@ -222,6 +445,7 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::Update,
             Capability::Upgrade,
             Capability::UpdateIndex,
+            Capability::Info,
         ])
     }
     fn supported_platforms(&self) -> Vec<Platform> {
@@ -393,29 +617,47 @@ async fn uninstall(&self, req: UninstallRequest) -> CogniaResult<()> {
     }
 
     async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<InstalledPackage>> {
-        let out = self.run_scoop(&["list"]).await?;
+        let apps = match self.export().await {
+            Ok(export) => export.apps,
+            Err(_) => {
+                let out = self.run_scoop(&["list"]).await?;
+                Self::parse_list_output(&out)
+                    .into_iter()
+                    .map(|(name, version, _source)| ScoopApp {
+                        name,
+                        version,
+                        global: false,
+                        held: false,
+                    })
+                    .collect()
+            }
+        };
         let scoop_dir = Self::get_scoop_dir().unwrap_or_default();
+        let global_dir = Self::get_scoop_global_dir();
 
-        let parsed = Self::parse_list_output(&out);
-
-        Ok(parsed
+        Ok(apps
             .into_iter()
-            .filter(|(name, _, _)| {
+            .filter(|app| {
+                if filter.global_only && !app.global {
+                    return false;
+                }
                 if let Some(ref name_filter) = filter.name_filter {
-                    name.contains(name_filter)
+                    app.name.contains(name_filter)
                 } else {
                     true
                 }
             })
-            .map(|(name, version, _source)| {
-                let install_path = scoop_dir.join("apps").join(&name).join("current");
+            .map(|app| {
+                let root = if app.global { &global_dir } else { &scoop_dir };
+                let install_path = root.join("apps").join(&app.name).join("current");
                 InstalledPackage {
-                    name,
-                    version,
+                    name: app.name,
+                    version: app.version,
                     provider: self.id().into(),
                     install_path,
                     installed_at: String::new(),
-                    is_global: false,
+                    is_global: app.global,
+                    kind: None,
                 }
             })
             .collect())
@@ -446,6 +688,42 @@ async fn check_updates(&self, packages: &[String]) -> CogniaResult<Vec<UpdateInf
             })
             .collect())
     }
+
+    async fn health_metrics(&self) -> Vec<ProviderHealthMetric> {
+        // `--local` skips the bucket fetch so the probe stays fast
+        let (export, status) = tokio::join!(self.export(), self.run_scoop(&["status", "--local"]));
+
+        let mut metrics = Vec::new();
+        if let Ok(export) = export {
+            metrics.push(ProviderHealthMetric::new(
+                "buckets",
+                "Buckets",
+                export.buckets.len() as u64,
+            ));
+            metrics.push(ProviderHealthMetric::new(
+                "installed",
+                "Installed apps",
+                export.apps.len() as u64,
+            ));
+            metrics.push(ProviderHealthMetric::new(
+                "held",
+                "Held apps",
+                export.apps.iter().filter(|app| app.held).count() as u64,
+            ));
+        }
+        if let Ok(out) = status {
+            let outdated = Self::parse_status_output(&out)
+                .iter()
+                .filter(|(_, current, latest)| current != latest)
+                .count();
+            metrics.push(ProviderHealthMetric::new(
+                "outdated",
+                "Outdated apps",
+                outdated as u64,
+            ));
+        }
+        metrics
+    }
 }
 
 #[async_trait]
@@ -454,8 +732,9 @@ async fn check_system_requirements(&self) -> CogniaResult<bool> {
         Ok(self.is_available().await)
     }
 
-    fn requires_elevation(&self, _operation: &str) -> bool {
-        false // Scoop is user-space, no admin required
+    fn requires_elevation(&self, operation: &str) -> bool {
+        // Scoop is user-space; only `--global` operations write to ProgramData
+        operation.ends_with("_global")
     }
 
     async fn get_version(&self) -> CogniaResult<String> {
@@ -630,6 +909,52 @@ fn test_requires_elevation() {
         assert!(!SystemPackageProvider::requires_elevation(
             &provider, "upgrade"
         ));
+        assert!(SystemPackageProvider::requires_elevation(
+            &provider,
+            "install_global"
+        ));
+        assert_eq!(
+            provider.elevated_operations(),
+            vec!["install_global", "uninstall_global", "cleanup_global"]
+        );
+    }
+
+    #[test]
+    fn test_parse_export() {
+        let output = "\u{feff}{\"buckets\":[{\"Name\":\"main\",\"Source\":\"https://github.com/ScoopInstaller/Main\",\"Updated\":{\"value\":\"2024-01-15T10:30:00\",\"DateTime\":\"Monday, 15 January 2024\"},\"Manifests\":1342}],\"apps\":[{\"Name\":\"7zip\",\"Version\":\"23.01\",\"Source\":\"main\",\"Updated\":\"2024-01-15 10:30:00\",\"Info\":\"\"},{\"Name\":\"git\",\"Version\":\"2.42.0\",\"Source\":\"main\",\"Updated\":\"2024-01-10 08:20:00\",\"Info\":\"Global install, Held package\"}]}";
+        let export = ScoopProvider::parse_export(output).unwrap();
+        assert_eq!(
+            export.buckets,
+            vec![ScoopBucket {
+                name: "main".into(),
+                source: "https://github.com/ScoopInstaller/Main".into(),
+                updated: Some("2024-01-15T10:30:00".into()),
+                manifests: Some(1342),
+            }]
+        );
+        assert_eq!(export.apps.len(), 2);
+        assert_eq!(export.apps[0].name, "7zip");
+        assert!(!export.apps[0].global);
+        assert_eq!(export.apps[1].version, "2.42.0");
+        assert!(export.apps[1].global);
+        assert!(export.apps[1].held);
+    }
+
+    #[test]
+    fn test_parse_export_legacy_text() {
+        let output = "7zip (v:23.01) [main]\ngit (v:2.42.0) *global* [main]\n";
+        assert!(ScoopProvider::parse_export(output).is_none());
+    }
+
+    #[test]
+    fn test_parse_bucket_list() {
+        let output = "\nName    Source                                   Updated            Manifests\n----    ------                                   -------            ---------\nmain    https://github.com/ScoopInstaller/Main   2024-01-15 10:30:00      1342\nextras  https://github.com/ScoopInstaller/Extras 2024-01-14 09:00:00      2011\n";
+        let results = ScoopProvider::parse_bucket_list(output);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].name, "main");
+        assert_eq!(results[0].source, "https://github.com/ScoopInstaller/Main");
+        assert_eq!(results[0].updated, Some("2024-01-15 10:30:00".to_string()));
+        assert_eq!(results[1].manifests, Some(2011));
     }
 
     #[test]
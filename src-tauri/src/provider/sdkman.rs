@@ -1,10 +1,13 @@
+use super::install_script::{self, InstallScript};
 use super::traits::*;
+use crate::core::custom_env_type::validate_version;
 use crate::error::{CogniaError, CogniaResult};
 use crate::platform::{
     env::{dirs_home, EnvModifications, Platform},
     process::{self, ProcessOptions},
 };
 use async_trait::async_trait;
+use serde::Serialize;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
@@ -26,19 +29,50 @@ fn detect_sdkman_dir() -> Option<PathBuf> {
     dirs_home().map(|h| h.join(".sdkman"))
 }
 
+/// Installer script; `rcupdate=true` has it add the init snippet to shell profiles
+const SDKMAN_INSTALL_SCRIPT_URL: &str = "https://get.sdkman.io?rcupdate=true";
+
+/// Shell profiles the installer writes its init snippet to
+const SDKMAN_PROFILE_FILES: &[&str] = &[".bashrc", ".bash_profile", ".zshrc", ".profile"];
+
+/// How long an `sdk` listing or switch may take
+const SDK_COMMAND_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// How long `sdk install` may take; JDK archives are a few hundred MB
+const SDK_INSTALL_TIMEOUT: Duration = Duration::from_secs(1800);
+
+fn shell_quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
 /// Run an `sdk` subcommand via shell sourcing
 async fn run_sdk(sdkman_dir: &Path, args: &[&str]) -> CogniaResult<String> {
+    run_sdk_with_timeout(sdkman_dir, args, SDK_COMMAND_TIMEOUT).await
+}
+
+/// Run an `sdk` subcommand in a non-interactive bash login shell
+async fn run_sdk_with_timeout(
+    sdkman_dir: &Path,
+    args: &[&str],
+    timeout: Duration,
+) -> CogniaResult<String> {
     let init_script = sdkman_dir.join("bin").join("sdkman-init.sh");
 
-    // SDKMAN_COLOUR_ENABLE=false suppresses ANSI codes in output
+    // The init script loads `etc/config`, so the overrides come after it:
+    // prompts take their default answer and output has no ANSI codes.
+    // PAGER=cat keeps long listings from going through `less`.
+    let quoted: Vec<String> = args.iter().map(|a| shell_quote(a)).collect();
     let cmd = format!(
-        "export SDKMAN_COLOUR_ENABLE=false && source \"{}\" && sdk {}",
-        init_script.display(),
-        args.join(" ")
+        "source {} && export sdkman_auto_answer=true sdkman_colour_enable=false && sdk {}",
+        shell_quote(&init_script.to_string_lossy()),
+        quoted.join(" ")
     );
 
-    let opts = ProcessOptions::new().with_timeout(Duration::from_secs(120));
-    let output = process::execute_shell(&cmd, Some(opts)).await?;
+    let opts = ProcessOptions::new()
+        .with_timeout(timeout)
+        .with_env("SDKMAN_DIR", sdkman_dir.to_string_lossy())
+        .with_env("PAGER", "cat");
+    let output = process::execute("bash", &["-lc", &cmd], Some(opts)).await?;
 
     if output.success {
         Ok(clean_sdk_output(&output.stdout))
@@ -82,44 +116,52 @@ fn clean_sdk_output(input: &str) -> String {
 
 /// Parse the pipe-delimited `sdk list java` output into (vendor, version_str, dist, status, identifier) tuples.
 /// Lines look like: ` Vendor        | Use | Version      | Dist    | Status     | Identifier`
+///
+/// Rows after a vendor's first leave the vendor column blank, and a paged
+/// listing repeats the header, so vendors carry over and identifiers are
+/// deduplicated.
 pub fn parse_sdk_list_java(output: &str) -> Vec<SdkListEntry> {
-    output
-        .lines()
-        .filter_map(|line| {
-            let trimmed = line.trim();
-            if trimmed.is_empty()
-                || trimmed.starts_with('=')
-                || trimmed.starts_with("Available")
-                || trimmed.starts_with("Vendor")
-                || trimmed.starts_with("Use the")
-                || trimmed.starts_with("---")
-            {
-                return None;
-            }
-            let parts: Vec<&str> = line.split('|').map(|s| s.trim()).collect();
-            if parts.len() >= 6 {
-                let vendor = parts[0].to_string();
-                let identifier = parts[5].to_string();
-                let status = parts[4].to_string();
-                let version_str = parts[2].to_string();
-                Some(SdkListEntry {
-                    vendor,
-                    version: version_str,
-                    identifier,
-                    installed: status.contains("installed") || status.contains("local"),
-                    in_use: status.contains(">>>") || parts[1].contains(">>>"),
-                })
-            } else {
-                None
-            }
-        })
-        .collect()
+    let mut entries: Vec<SdkListEntry> = Vec::new();
+    let mut vendor = String::new();
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty()
+            || trimmed.starts_with('=')
+            || trimmed.starts_with("Available")
+            || trimmed.starts_with("Vendor")
+            || trimmed.starts_with("Use the")
+            || trimmed.starts_with("---")
+        {
+            continue;
+        }
+        let parts: Vec<&str> = line.split('|').map(|s| s.trim()).collect();
+        if parts.len() < 6 || parts[5].is_empty() {
+            continue;
+        }
+        if !parts[0].is_empty() {
+            vendor = parts[0].to_string();
+        }
+        let identifier = parts[5].to_string();
+        if entries.iter().any(|e| e.identifier == identifier) {
+            continue;
+        }
+        let status = parts[4];
+        entries.push(SdkListEntry {
+            vendor: vendor.clone(),
+            version: parts[2].to_string(),
+            identifier,
+            installed: status.contains("installed") || status.contains("local"),
+            in_use: status.contains(">>>") || parts[1].contains(">>>"),
+        });
+    }
+    entries
 }
 
 /// Parse the simpler `sdk list <candidate>` output for non-java candidates (Kotlin, Gradle, etc.)
 /// These produce a grid of version strings separated by whitespace.
+/// Versions keep the listing's order; ones repeated by a paged listing are skipped.
 pub fn parse_sdk_list_versions(output: &str) -> Vec<String> {
-    let mut versions = Vec::new();
+    let mut versions: Vec<String> = Vec::new();
     let mut in_versions = false;
     for line in output.lines() {
         let trimmed = line.trim();
@@ -133,10 +175,17 @@ pub fn parse_sdk_list_versions(output: &str) -> Vec<String> {
         if trimmed.is_empty() || trimmed.starts_with("Use the") {
             continue;
         }
-        // Versions are separated by whitespace, with optional > marker and * for installed
+        // Versions are separated by whitespace, with > (in use), * (installed)
+        // and + (local) markers either standalone or attached
         for tok in trimmed.split_whitespace() {
-            let v = tok.trim_start_matches('>').trim_end_matches('*').trim();
-            if !v.is_empty() && v.chars().next().is_some_and(|c| c.is_ascii_digit()) {
+            let v = tok
+                .trim_start_matches(['>', '*', '+'])
+                .trim_end_matches('*')
+                .trim();
+            if !v.is_empty()
+                && v.chars().next().is_some_and(|c| c.is_ascii_digit())
+                && !versions.iter().any(|known| known == v)
+            {
                 versions.push(v.to_string());
             }
         }
@@ -358,6 +407,132 @@ pub fn normalize_detected_version(raw: &str) -> Option<String> {
     Some(candidate.to_string())
 }
 
+/// Point `<candidate>/current` at `target` the way `sdk default` does, which
+/// switches the version for every shell at once. The new link is renamed
+/// over the old one, so `current` never goes missing mid-switch.
+#[cfg(unix)]
+fn link_current(candidate_dir: &Path, target: &Path) -> CogniaResult<()> {
+    let current = candidate_dir.join("current");
+    if std::fs::symlink_metadata(&current).is_ok_and(|meta| meta.is_dir()) {
+        return Err(CogniaError::Conflict(format!(
+            "{} is a directory, not a link; move it aside to switch versions",
+            current.display()
+        )));
+    }
+
+    let staged = candidate_dir.join(format!(".current-{}", std::process::id()));
+    let _ = std::fs::remove_file(&staged);
+    std::os::unix::fs::symlink(target, &staged)?;
+    if let Err(e) = std::fs::rename(&staged, &current) {
+        let _ = std::fs::remove_file(&staged);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn link_current(_candidate_dir: &Path, _target: &Path) -> CogniaResult<()> {
+    Err(CogniaError::Provider(
+        "SDKMAN is not supported on Windows".into(),
+    ))
+}
+
+/// Whether a shell profile sources `sdkman-init.sh`
+pub fn has_sdkman_init_line(profile: &str) -> bool {
+    profile.lines().any(|line| {
+        let line = line.trim();
+        !line.starts_with('#') && line.contains("sdkman-init.sh")
+    })
+}
+
+/// Outcome of [`self_install`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SdkmanSelfInstall {
+    pub sdkman_dir: String,
+    pub version: Option<String>,
+    /// Shell profiles that source `sdkman-init.sh`; empty means new shells
+    /// will not have `sdk` until the user adds the init line
+    pub init_profiles: Vec<String>,
+    pub output: String,
+}
+
+/// Download the SDKMAN installer for the user to review; pass its hash to
+/// [`self_install`] to run it
+pub async fn install_script_preview() -> CogniaResult<InstallScript> {
+    install_script::fetch("sdkman", SDKMAN_INSTALL_SCRIPT_URL).await
+}
+
+/// Run the official SDKMAN installer, if it still matches the reviewed
+/// `expected_sha256`, and check its result
+pub async fn self_install(expected_sha256: &str) -> CogniaResult<SdkmanSelfInstall> {
+    if cfg!(windows) {
+        return Err(CogniaError::Installation(
+            "SDKMAN is not supported on Windows".into(),
+        ));
+    }
+
+    let mut missing = Vec::new();
+    for cmd in ["curl", "zip", "unzip", "bash"] {
+        if process::which(cmd).await.is_none() {
+            missing.push(cmd);
+        }
+    }
+    if !missing.is_empty() {
+        return Err(CogniaError::Installation(format!(
+            "The SDKMAN installer needs {} on PATH",
+            missing.join(", ")
+        )));
+    }
+
+    let output = install_script::run(
+        "sdkman",
+        SDKMAN_INSTALL_SCRIPT_URL,
+        expected_sha256,
+        "bash",
+        &[],
+        Duration::from_secs(600),
+    )
+    .await?;
+    if !output.success {
+        return Err(CogniaError::Installation(
+            if output.stderr.trim().is_empty() {
+                output.stdout
+            } else {
+                output.stderr
+            },
+        ));
+    }
+
+    let sdkman_dir = detect_sdkman_dir()
+        .filter(|dir| dir.join("bin").join("sdkman-init.sh").exists())
+        .ok_or_else(|| {
+            CogniaError::Installation(
+                "SDKMAN installer finished but sdkman-init.sh was not found".into(),
+            )
+        })?;
+    let version = SdkmanProvider::java().get_version().await.ok();
+
+    let mut init_profiles = Vec::new();
+    if let Some(home) = dirs_home() {
+        for name in SDKMAN_PROFILE_FILES {
+            let path = home.join(name);
+            if let Ok(content) = crate::platform::fs::read_file_string(&path).await {
+                if has_sdkman_init_line(&content) {
+                    init_profiles.push(path.display().to_string());
+                }
+            }
+        }
+    }
+
+    Ok(SdkmanSelfInstall {
+        sdkman_dir: sdkman_dir.display().to_string(),
+        version,
+        init_profiles,
+        output: clean_sdk_output(&output.stdout),
+    })
+}
+
 #[derive(Debug, Clone)]
 pub struct SdkListEntry {
     pub vendor: String,
@@ -518,6 +693,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::VersionSwitch,
             Capability::MultiVersion,
             Capability::ProjectLocal,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
 
@@ -534,6 +711,10 @@ fn priority(&self) -> i32 {
     }
 
     async fn is_available(&self) -> bool {
+        // SDKMAN is bash-only; Git Bash or WSL installs are not managed here
+        if cfg!(windows) {
+            return false;
+        }
         let Some(sdkman_dir) = &self.sdkman_dir else {
             return false;
         };
@@ -690,9 +871,15 @@ async fn install(&self, req: InstallRequest) -> CogniaResult<InstallReceipt> {
         let version = req
             .version
             .ok_or_else(|| CogniaError::Provider("Version required for SDKMAN install".into()))?;
+        validate_version(&version)?;
 
-        self.run_sdk(&["install", &self.candidate, &version])
-            .await?;
+        let dir = self.sdkman_dir()?;
+        run_sdk_with_timeout(
+            &dir,
+            &["install", &self.candidate, &version],
+            SDK_INSTALL_TIMEOUT,
+        )
+        .await?;
 
         let dir = self.candidates_dir()?;
         let install_path = dir.join(&version);
@@ -711,6 +898,7 @@ async fn uninstall(&self, req: UninstallRequest) -> CogniaResult<()> {
         let version = req
             .version
             .ok_or_else(|| CogniaError::Provider("Version required for SDKMAN uninstall".into()))?;
+        validate_version(&version)?;
 
         self.run_sdk(&["uninstall", &self.candidate, &version])
             .await?;
@@ -748,6 +936,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                             install_path: entry.path(),
                             installed_at: String::new(),
                             is_global: true,
+                            kind: None,
                         });
                     }
                 }
@@ -880,11 +1069,20 @@ async fn get_current_version(&self) -> CogniaResult<Option<String>> {
     }
 
     async fn set_global_version(&self, version: &str) -> CogniaResult<()> {
-        self.run_sdk(&["default", &self.candidate, version]).await?;
-        Ok(())
+        validate_version(version)?;
+        let dir = self.candidates_dir()?;
+        let target = dir.join(version);
+        if !target.is_dir() {
+            return Err(CogniaError::Provider(format!(
+                "{} {} is not installed",
+                self.candidate, version
+            )));
+        }
+        link_current(&dir, &target)
     }
 
     async fn set_local_version(&self, project_path: &Path, version: &str) -> CogniaResult<()> {
+        validate_version(version)?;
         // Update or create .sdkmanrc file preserving other candidates
         let sdkmanrc = project_path.join(".sdkmanrc");
         let mut entries: Vec<(String, String)> = if sdkmanrc.exists() {
@@ -1230,6 +1428,128 @@ fn test_parse_sdk_list_versions_with_markers() {
         assert!(versions.contains(&"8.3".to_string()));
     }
 
+    #[test]
+    fn test_parse_sdk_list_versions_paged_with_local_and_legend() {
+        let output = r#"
+================================================================================
+Available Scala Versions
+================================================================================
+ > * 3.4.2               3.3.3               + 3.5.0-local
+     3.4.1               3.3.1
+================================================================================
+Available Scala Versions
+================================================================================
+     3.3.1               2.13.14
+================================================================================
++ - local version
+* - installed
+> - currently in use
+================================================================================
+"#;
+        let versions = parse_sdk_list_versions(output);
+        assert_eq!(
+            versions,
+            vec!["3.4.2", "3.3.3", "3.5.0-local", "3.4.1", "3.3.1", "2.13.14"]
+        );
+    }
+
+    #[test]
+    fn test_parse_sdk_list_java_carries_vendor_and_dedupes_pages() {
+        let output = r#"
+================================================================================
+Available Java Versions for Linux 64bit
+================================================================================
+ Vendor        | Use | Version      | Dist    | Status     | Identifier
+--------------------------------------------------------------------------------
+ Temurin       |     | 21.0.4       | tem     |            | 21.0.4-tem
+               | >>> | 17.0.9       | tem     | installed  | 17.0.9-tem
+               |     | 11.0.20      | tem     | local only | 11.0.20-tem
+ Vendor        | Use | Version      | Dist    | Status     | Identifier
+ Temurin       |     | 21.0.4       | tem     |            | 21.0.4-tem
+ Zulu          |     | 21.0.4       | zulu    |            | 21.0.4-zulu
+================================================================================
+"#;
+        let entries = parse_sdk_list_java(output);
+        let ids: Vec<&str> = entries.iter().map(|e| e.identifier.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["21.0.4-tem", "17.0.9-tem", "11.0.20-tem", "21.0.4-zulu"]
+        );
+        assert_eq!(entries[1].vendor, "Temurin");
+        assert!(entries[1].in_use);
+        assert!(entries[2].installed);
+        assert_eq!(entries[3].vendor, "Zulu");
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("17.0.9-tem"), "'17.0.9-tem'");
+        assert_eq!(shell_quote("a'b"), r"'a'\''b'");
+    }
+
+    #[test]
+    fn test_has_sdkman_init_line() {
+        let profile = r#"
+export PATH="$HOME/bin:$PATH"
+#THIS MUST BE AT THE END OF THE FILE FOR SDKMAN TO WORK!!!
+export SDKMAN_DIR="$HOME/.sdkman"
+[[ -s "$HOME/.sdkman/bin/sdkman-init.sh" ]] && source "$HOME/.sdkman/bin/sdkman-init.sh"
+"#;
+        assert!(has_sdkman_init_line(profile));
+        assert!(!has_sdkman_init_line(
+            "# source \"$HOME/.sdkman/bin/sdkman-init.sh\"\nexport PATH=/usr/bin\n"
+        ));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_set_global_version_relinks_current() {
+        let tmp = tempfile::tempdir().unwrap();
+        let candidate_dir = tmp.path().join("candidates").join("java");
+        std::fs::create_dir_all(candidate_dir.join("17.0.9-tem")).unwrap();
+        std::fs::create_dir_all(candidate_dir.join("21.0.4-tem")).unwrap();
+        std::os::unix::fs::symlink(
+            candidate_dir.join("17.0.9-tem"),
+            candidate_dir.join("current"),
+        )
+        .unwrap();
+
+        let provider = SdkmanProvider {
+            candidate: "java".into(),
+            sdkman_dir: Some(tmp.path().to_path_buf()),
+        };
+        provider.set_global_version("21.0.4-tem").await.unwrap();
+        assert_eq!(
+            std::fs::read_link(candidate_dir.join("current")).unwrap(),
+            candidate_dir.join("21.0.4-tem")
+        );
+        assert_eq!(
+            provider.get_current_version().await.unwrap().as_deref(),
+            Some("21.0.4-tem")
+        );
+        assert!(provider.set_global_version("8.0.392-tem").await.is_err());
+        assert!(provider.set_global_version("../../etc").await.is_err());
+        assert!(!candidate_dir
+            .join(format!(".current-{}", std::process::id()))
+            .exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_set_global_version_keeps_real_current_directory() {
+        let tmp = tempfile::tempdir().unwrap();
+        let candidate_dir = tmp.path().join("candidates").join("java");
+        std::fs::create_dir_all(candidate_dir.join("21.0.4-tem")).unwrap();
+        std::fs::create_dir_all(candidate_dir.join("current").join("bin")).unwrap();
+
+        let provider = SdkmanProvider {
+            candidate: "java".into(),
+            sdkman_dir: Some(tmp.path().to_path_buf()),
+        };
+        assert!(provider.set_global_version("21.0.4-tem").await.is_err());
+        assert!(candidate_dir.join("current").join("bin").is_dir());
+    }
+
     #[test]
     fn test_parse_sdkmanrc() {
         let content = r#"
@@ -0,0 +1,447 @@
+//! Schema self-tests for the registry APIs that providers parse.
+//!
+//! Each supported provider has one canned query. Its response is checked
+//! field by field against the shape the provider expects, then deserialized
+//! into the same types the provider uses, so upstream API changes show up
+//! as findings instead of silently empty results.
+
+use super::api::{get_api_client, CrateDetailResponse, NpmSearchResponse, PyPIResponse};
+use super::github::GitHubRelease;
+use crate::error::{CogniaError, CogniaResult};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Instant;
+
+/// Providers with a canned self-test query
+pub const SELFTEST_PROVIDERS: &[&str] = &["cargo", "github", "npm", "pip"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingSeverity {
+    /// The provider cannot parse the response
+    Error,
+    /// The response parses, but looks different from what was expected
+    Warning,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelftestFinding {
+    pub severity: FindingSeverity,
+    /// Location in the response, e.g. `objects[0].package.version`
+    pub path: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderSelftestReport {
+    pub provider: String,
+    pub endpoint: String,
+    /// No errors were found and the endpoint answered
+    pub compatible: bool,
+    pub duration_ms: u64,
+    pub findings: Vec<SelftestFinding>,
+    /// Set when the endpoint could not be reached or returned non-JSON
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    String,
+    Number,
+    Bool,
+    Array,
+    Object,
+}
+
+impl Kind {
+    fn matches(self, value: &Value) -> bool {
+        match self {
+            Kind::String => value.is_string(),
+            Kind::Number => value.is_number(),
+            Kind::Bool => value.is_boolean(),
+            Kind::Array => value.is_array(),
+            Kind::Object => value.is_object(),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Kind::String => "string",
+            Kind::Number => "number",
+            Kind::Bool => "boolean",
+            Kind::Array => "array",
+            Kind::Object => "object",
+        }
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Expected field. `path` is dot-separated; a `[]` suffix applies the rest of
+/// the path to every element of an array (`[]` alone is the root array).
+#[derive(Debug, Clone, Copy)]
+struct FieldCheck {
+    path: &'static str,
+    kind: Kind,
+    required: bool,
+}
+
+const fn required(path: &'static str, kind: Kind) -> FieldCheck {
+    FieldCheck {
+        path,
+        kind,
+        required: true,
+    }
+}
+
+const fn optional(path: &'static str, kind: Kind) -> FieldCheck {
+    FieldCheck {
+        path,
+        kind,
+        required: false,
+    }
+}
+
+const NPM_SEARCH_FIELDS: &[FieldCheck] = &[
+    required("objects", Kind::Array),
+    required("objects[].package", Kind::Object),
+    required("objects[].package.name", Kind::String),
+    required("objects[].package.version", Kind::String),
+    optional("objects[].package.description", Kind::String),
+    optional("objects[].package.keywords", Kind::Array),
+    optional("objects[].package.links", Kind::Object),
+    optional("objects[].score.final", Kind::Number),
+    required("total", Kind::Number),
+];
+
+const PYPI_PROJECT_FIELDS: &[FieldCheck] = &[
+    required("info", Kind::Object),
+    required("info.name", Kind::String),
+    required("info.version", Kind::String),
+    optional("info.summary", Kind::String),
+    optional("info.home_page", Kind::String),
+    optional("info.license", Kind::String),
+    optional("info.author", Kind::String),
+    optional("releases", Kind::Object),
+];
+
+const GITHUB_RELEASES_FIELDS: &[FieldCheck] = &[
+    required("[]", Kind::Object),
+    required("[].id", Kind::Number),
+    required("[].tag_name", Kind::String),
+    optional("[].name", Kind::String),
+    optional("[].body", Kind::String),
+    optional("[].published_at", Kind::String),
+    required("[].prerelease", Kind::Bool),
+    required("[].draft", Kind::Bool),
+    required("[].assets", Kind::Array),
+    required("[].assets[].id", Kind::Number),
+    required("[].assets[].name", Kind::String),
+    required("[].assets[].size", Kind::Number),
+    required("[].assets[].browser_download_url", Kind::String),
+    optional("[].assets[].content_type", Kind::String),
+    optional("[].assets[].download_count", Kind::Number),
+];
+
+const CRATES_DETAIL_FIELDS: &[FieldCheck] = &[
+    required("crate", Kind::Object),
+    required("crate.name", Kind::String),
+    required("crate.max_version", Kind::String),
+    optional("crate.description", Kind::String),
+    optional("crate.homepage", Kind::String),
+    optional("crate.documentation", Kind::String),
+    optional("crate.repository", Kind::String),
+    optional("crate.downloads", Kind::Number),
+    required("versions", Kind::Array),
+    required("versions[].num", Kind::String),
+    optional("versions[].yanked", Kind::Bool),
+];
+
+struct CannedQuery {
+    endpoint: String,
+    fields: &'static [FieldCheck],
+    /// Name of the type the provider deserializes into, for messages
+    target: &'static str,
+    parse: fn(&Value) -> Result<(), String>,
+}
+
+fn parses_as<T: DeserializeOwned>(body: &Value) -> Result<(), String> {
+    T::deserialize(body).map(|_| ()).map_err(|e| e.to_string())
+}
+
+fn canned_query(provider: &str) -> Option<CannedQuery> {
+    let api = get_api_client();
+    let query = match provider {
+        "npm" => CannedQuery {
+            endpoint: format!("{}/-/v1/search?text=react&size=2", api.get_npm_registry()),
+            fields: NPM_SEARCH_FIELDS,
+            target: "NpmSearchResponse",
+            parse: parses_as::<NpmSearchResponse>,
+        },
+        "pip" => CannedQuery {
+            endpoint: format!("{}/pypi/requests/json", api.get_pypi_url()),
+            fields: PYPI_PROJECT_FIELDS,
+            target: "PyPIResponse",
+            parse: parses_as::<PyPIResponse>,
+        },
+        "github" => CannedQuery {
+            endpoint: "https://api.github.com/repos/cli/cli/releases?per_page=20".into(),
+            fields: GITHUB_RELEASES_FIELDS,
+            target: "GitHubRelease list",
+            parse: parses_as::<Vec<GitHubRelease>>,
+        },
+        "cargo" => CannedQuery {
+            endpoint: format!("{}/api/v1/crates/serde", api.get_crates_registry()),
+            fields: CRATES_DETAIL_FIELDS,
+            target: "CrateDetailResponse",
+            parse: parses_as::<CrateDetailResponse>,
+        },
+        _ => return None,
+    };
+    Some(query)
+}
+
+/// Run the canned query for `provider` and report how well the response
+/// matches what the provider parses
+pub async fn run_selftest(provider: &str) -> CogniaResult<ProviderSelftestReport> {
+    let query = canned_query(provider).ok_or_else(|| {
+        CogniaError::Provider(format!(
+            "No self-test for provider {}. Available: {}",
+            provider,
+            SELFTEST_PROVIDERS.join(", ")
+        ))
+    })?;
+
+    let started = Instant::now();
+    let body = get_api_client().raw_get(&query.endpoint).await;
+    let duration_ms = started.elapsed().as_millis() as u64;
+
+    let mut report = ProviderSelftestReport {
+        provider: provider.to_string(),
+        endpoint: query.endpoint.clone(),
+        compatible: false,
+        duration_ms,
+        findings: Vec::new(),
+        error: None,
+    };
+
+    let body = match body.and_then(|text| {
+        serde_json::from_str::<Value>(&text).map_err(|e| CogniaError::Parse(e.to_string()))
+    }) {
+        Ok(body) => body,
+        Err(e) => {
+            report.error = Some(e.to_string());
+            return Ok(report);
+        }
+    };
+
+    report.findings = check_fields(&body, query.fields);
+    if let Err(e) = (query.parse)(&body) {
+        report.findings.push(SelftestFinding {
+            severity: FindingSeverity::Error,
+            path: "$".into(),
+            message: format!("Does not deserialize into {}: {}", query.target, e),
+        });
+    }
+    report.compatible = !report
+        .findings
+        .iter()
+        .any(|f| f.severity == FindingSeverity::Error);
+    Ok(report)
+}
+
+fn check_fields(body: &Value, checks: &[FieldCheck]) -> Vec<SelftestFinding> {
+    let mut findings = Vec::new();
+    for check in checks {
+        let segments: Vec<&str> = check.path.split('.').collect();
+        let mut found = Vec::new();
+        resolve(body, String::new(), &segments, &mut found, &mut findings);
+
+        let mut present = 0;
+        for (path, value) in &found {
+            match value {
+                None if check.required => findings.push(SelftestFinding {
+                    severity: FindingSeverity::Error,
+                    path: path.clone(),
+                    message: format!("Missing required {}", check.kind.name()),
+                }),
+                None => {}
+                Some(Value::Null) if !check.required => present += 1,
+                Some(value) => {
+                    present += 1;
+                    if !check.kind.matches(value) {
+                        findings.push(SelftestFinding {
+                            severity: FindingSeverity::Error,
+                            path: path.clone(),
+                            message: format!(
+                                "Expected {}, found {}",
+                                check.kind.name(),
+                                json_type(value)
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+
+        if !check.required && present == 0 && !found.is_empty() {
+            findings.push(SelftestFinding {
+                severity: FindingSeverity::Warning,
+                path: check.path.to_string(),
+                message: "Never present in the response; it may have been renamed".into(),
+            });
+        }
+    }
+    findings
+}
+
+/// Collect the values `segments` point at, `None` where a field is absent.
+/// Shape problems on the way (a non-array under `[]`) become findings.
+fn resolve<'a>(
+    value: &'a Value,
+    at: String,
+    segments: &[&str],
+    found: &mut Vec<(String, Option<&'a Value>)>,
+    findings: &mut Vec<SelftestFinding>,
+) {
+    let Some((segment, rest)) = segments.split_first() else {
+        found.push((at, Some(value)));
+        return;
+    };
+
+    let (field, each) = match segment.strip_suffix("[]") {
+        Some(field) => (field, true),
+        None => (*segment, false),
+    };
+    let (value, at) = if field.is_empty() {
+        (value, at)
+    } else {
+        let at = if at.is_empty() {
+            field.to_string()
+        } else {
+            format!("{}.{}", at, field)
+        };
+        match value.get(field) {
+            Some(value) => (value, at),
+            None => {
+                found.push((at, None));
+                return;
+            }
+        }
+    };
+
+    if !each {
+        resolve(value, at, rest, found, findings);
+        return;
+    }
+    match value.as_array() {
+        Some(items) if items.is_empty() => findings.push(SelftestFinding {
+            severity: FindingSeverity::Warning,
+            path: format!("{}[]", at),
+            message: "Empty array; element fields were not checked".into(),
+        }),
+        Some(items) => {
+            for (index, item) in items.iter().enumerate() {
+                resolve(item, format!("{}[{}]", at, index), rest, found, findings);
+            }
+        }
+        None if value.is_null() => {}
+        None => findings.push(SelftestFinding {
+            severity: FindingSeverity::Error,
+            path: if at.is_empty() { "$".into() } else { at },
+            message: format!("Expected array, found {}", json_type(value)),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_check_fields_matching_response_has_no_findings() {
+        let body = json!({
+            "objects": [
+                { "package": { "name": "a", "version": "1.0.0", "description": null } },
+                { "package": { "name": "b", "version": "2.0.0", "description": "b" } }
+            ],
+            "total": 2
+        });
+        let checks = [
+            required("objects[].package.name", Kind::String),
+            required("objects[].package.version", Kind::String),
+            optional("objects[].package.description", Kind::String),
+            required("total", Kind::Number),
+        ];
+        assert!(check_fields(&body, &checks).is_empty());
+    }
+
+    #[test]
+    fn test_check_fields_reports_missing_and_mistyped_fields() {
+        let body = json!({
+            "objects": [
+                { "package": { "name": "a", "version": 1 } },
+                { "package": { "version": "2.0.0" } }
+            ]
+        });
+        let checks = [
+            required("objects[].package.name", Kind::String),
+            required("objects[].package.version", Kind::String),
+            optional("objects[].package.description", Kind::String),
+            required("total", Kind::Number),
+        ];
+        let findings = check_fields(&body, &checks);
+        let summary: Vec<(FindingSeverity, &str)> = findings
+            .iter()
+            .map(|f| (f.severity, f.path.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (FindingSeverity::Error, "objects[1].package.name"),
+                (FindingSeverity::Error, "objects[0].package.version"),
+                (FindingSeverity::Warning, "objects[].package.description"),
+                (FindingSeverity::Error, "total"),
+            ]
+        );
+        assert_eq!(findings[1].message, "Expected string, found number");
+    }
+
+    #[test]
+    fn test_check_fields_root_array() {
+        let checks = [
+            required("[]", Kind::Object),
+            required("[].tag_name", Kind::String),
+        ];
+        let findings = check_fields(&json!({ "message": "Not Found" }), &checks);
+        assert!(findings
+            .iter()
+            .all(|f| f.severity == FindingSeverity::Error && f.path == "$"));
+
+        let findings = check_fields(&json!([]), &checks);
+        assert!(findings
+            .iter()
+            .all(|f| f.severity == FindingSeverity::Warning));
+    }
+
+    #[tokio::test]
+    async fn test_run_selftest_rejects_unknown_provider() {
+        let err = run_selftest("winget").await.unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Available: cargo, github, npm, pip"));
+    }
+}
@@ -67,6 +67,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::List,
             Capability::Update,
             Capability::Upgrade,
+            Capability::Info,
+            Capability::RequiresElevation,
         ])
     }
 
@@ -299,6 +301,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                         install_path: PathBuf::from(format!("/snap/{}", name)),
                         installed_at: String::new(),
                         is_global: true,
+                        kind: None,
                     })
                 } else {
                     None
@@ -443,7 +446,7 @@ fn test_capabilities() {
         assert!(caps.contains(&Capability::List));
         assert!(caps.contains(&Capability::Update));
         assert!(caps.contains(&Capability::Upgrade));
-        assert_eq!(caps.len(), 6);
+        assert_eq!(caps.len(), 8);
     }
 
     #[test]
@@ -533,6 +536,7 @@ fn test_parse_snap_list_output() {
                         install_path: PathBuf::from(format!("/snap/{}", name)),
                         installed_at: String::new(),
                         is_global: true,
+                        kind: None,
                     })
                 } else {
                     None
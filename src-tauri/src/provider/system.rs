@@ -44,6 +44,7 @@ pub enum SystemEnvironmentType {
     Fortran,
     C,
     Cpp,
+    Cuda,
 }
 
 impl SystemEnvironmentType {
@@ -79,6 +80,7 @@ pub fn id(&self) -> &'static str {
             Self::Fortran => "system-fortran",
             Self::C => "system-c",
             Self::Cpp => "system-cpp",
+            Self::Cuda => "system-cuda",
         }
     }
 
@@ -114,6 +116,7 @@ pub fn display_name(&self) -> &'static str {
             Self::Fortran => "Fortran (System)",
             Self::C => "C (System)",
             Self::Cpp => "C++ (System)",
+            Self::Cuda => "CUDA Toolkit (System)",
         }
     }
 
@@ -149,6 +152,7 @@ pub fn env_type(&self) -> &'static str {
             Self::Fortran => "fortran",
             Self::C => "c",
             Self::Cpp => "cpp",
+            Self::Cuda => "cuda",
         }
     }
 
@@ -395,6 +399,13 @@ pub fn detection_config(&self) -> SystemDetectionConfig {
                 version_files: vec![".tool-versions"],
                 manifest_files: vec![],
             },
+            Self::Cuda => SystemDetectionConfig {
+                commands: vec!["nvcc"],
+                version_args: vec!["--version"],
+                version_pattern: r"\bV(\d+\.\d+\.\d+)",
+                version_files: vec![".cuda-version", ".tool-versions"],
+                manifest_files: vec![],
+            },
         }
     }
 
@@ -431,6 +442,7 @@ pub fn all() -> Vec<Self> {
             Self::Fortran,
             Self::C,
             Self::Cpp,
+            Self::Cuda,
         ]
     }
 }
@@ -712,7 +724,7 @@ fn display_name(&self) -> &str {
     }
 
     fn capabilities(&self) -> HashSet<Capability> {
-        HashSet::from([Capability::List])
+        HashSet::from([Capability::List, Capability::Info])
     }
 
     fn supported_platforms(&self) -> Vec<Platform> {
@@ -732,18 +744,23 @@ async fn is_available(&self) -> bool {
     }
 
     async fn unavailable_reason(&self) -> Option<SupportReason> {
-        if !matches!(self.env_type, SystemEnvironmentType::Cpp) {
-            return None;
+        match self.env_type {
+            SystemEnvironmentType::Cpp => {
+                let commands = self.env_type.detection_config().commands.join(", ");
+                Some(SupportReason {
+                    code: "cpp-compiler-not-found",
+                    message: format!(
+                        "No runnable C++ compiler was found in PATH (checked {})",
+                        commands
+                    ),
+                })
+            }
+            SystemEnvironmentType::Cuda => Some(SupportReason {
+                code: "cuda-toolkit-not-found",
+                message: "No CUDA toolkit was found (nvcc is not in PATH)".into(),
+            }),
+            _ => None,
         }
-
-        let commands = self.env_type.detection_config().commands.join(", ");
-        Some(SupportReason {
-            code: "cpp-compiler-not-found",
-            message: format!(
-                "No runnable C++ compiler was found in PATH (checked {})",
-                commands
-            ),
-        })
     }
 
     async fn search(
@@ -799,6 +816,7 @@ async fn list_installed(
                 install_path: path,
                 installed_at: String::new(),
                 is_global: true,
+                kind: None,
             }])
         } else {
             Ok(vec![])
@@ -813,6 +831,21 @@ async fn check_updates(&self, _packages: &[String]) -> CogniaResult<Vec<UpdateIn
 #[async_trait]
 impl EnvironmentProvider for SystemEnvironmentProvider {
     async fn list_installed_versions(&self) -> CogniaResult<Vec<InstalledVersion>> {
+        // Several CUDA toolkits commonly live side by side; only one is on PATH
+        if matches!(self.env_type, SystemEnvironmentType::Cuda) {
+            return Ok(crate::provider::cuda::list_installed_toolkits()
+                .await
+                .into_iter()
+                .map(|toolkit| InstalledVersion {
+                    version: toolkit.version,
+                    install_path: toolkit.path,
+                    size: None,
+                    installed_at: None,
+                    is_current: toolkit.is_current,
+                })
+                .collect());
+        }
+
         if let Ok(Some((version, path))) = self.detect_system_version().await {
             Ok(vec![InstalledVersion {
                 version,
@@ -1267,6 +1300,7 @@ fn test_all_version_patterns() {
             (SystemEnvironmentType::C, "Apple clang version 16.0.0 (clang-1600.0.26.6)", "16.0.0"),
             (SystemEnvironmentType::Cpp, "g++ (GCC) 14.2.0", "14.2.0"),
             (SystemEnvironmentType::Cpp, "clang version 19.1.0", "19.1.0"),
+            (SystemEnvironmentType::Cuda, "Cuda compilation tools, release 12.4, V12.4.131", "12.4.131"),
         ];
 
         for (env_type, output, expected) in test_cases {
@@ -5,7 +5,7 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::mpsc;
 
@@ -18,12 +18,44 @@ pub enum Capability {
     Upgrade,
     Search,
     List,
+    /// Pin a package so updates leave it alone
     LockVersion,
     Rollback,
     VersionSwitch,
     MultiVersion,
     ProjectLocal,
     UpdateIndex,
+    /// Package details beyond the search summary
+    Info,
+    /// Lists installable versions and installs a chosen one, not only the latest
+    Versions,
+    /// Installing or uninstalling needs administrator or root rights
+    RequiresElevation,
+    /// Implements [`Provider::install_to_prefix`]
+    SupportsPrefix,
+}
+
+impl Capability {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Install => "install",
+            Self::Uninstall => "uninstall",
+            Self::Update => "update",
+            Self::Upgrade => "upgrade",
+            Self::Search => "search",
+            Self::List => "list",
+            Self::LockVersion => "lock_version",
+            Self::Rollback => "rollback",
+            Self::VersionSwitch => "version_switch",
+            Self::MultiVersion => "multi_version",
+            Self::ProjectLocal => "project_local",
+            Self::UpdateIndex => "update_index",
+            Self::Info => "info",
+            Self::Versions => "versions",
+            Self::RequiresElevation => "requires_elevation",
+            Self::SupportsPrefix => "supports_prefix",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,6 +86,73 @@ pub struct VersionInfo {
     pub yanked: bool,
 }
 
+/// Where the previous version sync left off.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VersionSyncCursor {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    /// Versions already known locally, so paginated sources can stop early
+    pub known_versions: HashSet<String>,
+}
+
+/// Outcome of an incremental version fetch.
+#[derive(Debug, Clone)]
+pub enum VersionFetch {
+    /// Upstream reported no change since the cursor
+    Unchanged,
+    /// The complete upstream list; anything missing from it was removed upstream
+    Full {
+        versions: Vec<VersionInfo>,
+        etag: Option<String>,
+        last_modified: Option<String>,
+    },
+    /// Only versions published since the cursor, newest first
+    Delta {
+        versions: Vec<VersionInfo>,
+        etag: Option<String>,
+        last_modified: Option<String>,
+    },
+}
+
+impl VersionFetch {
+    /// Diff a complete upstream `listing` against `cursor`, for sources that
+    /// can only be read whole. Only new versions are returned, unless one the
+    /// cursor knows has disappeared; then the full listing is, so the removal
+    /// is recorded.
+    pub fn from_listing(
+        listing: Vec<VersionInfo>,
+        cursor: &VersionSyncCursor,
+        etag: Option<String>,
+        last_modified: Option<String>,
+    ) -> Self {
+        let listed: HashSet<&str> = listing.iter().map(|info| info.version.as_str()).collect();
+        let removed = cursor
+            .known_versions
+            .iter()
+            .any(|version| !listed.contains(version.as_str()));
+        if cursor.known_versions.is_empty() || removed {
+            return Self::Full {
+                versions: listing,
+                etag,
+                last_modified,
+            };
+        }
+
+        let versions: Vec<VersionInfo> = listing
+            .into_iter()
+            .filter(|info| !cursor.known_versions.contains(&info.version))
+            .collect();
+        if versions.is_empty() && etag == cursor.etag && last_modified == cursor.last_modified {
+            return Self::Unchanged;
+        }
+        Self::Delta {
+            versions,
+            etag,
+            last_modified,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InstalledPackage {
     pub name: String,
@@ -62,6 +161,9 @@ pub struct InstalledPackage {
     pub install_path: PathBuf,
     pub installed_at: String,
     pub is_global: bool,
+    /// Provider-specific package kind, e.g. `formula` / `cask` for brew
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -265,6 +367,12 @@ async fn unavailable_reason(&self) -> Option<SupportReason> {
         None
     }
 
+    /// Manager-specific health figures; only queried once the provider is
+    /// known to be available.
+    async fn health_metrics(&self) -> Vec<ProviderHealthMetric> {
+        Vec::new()
+    }
+
     async fn search(
         &self,
         query: &str,
@@ -275,6 +383,20 @@ async fn search(
 
     async fn get_versions(&self, name: &str) -> CogniaResult<Vec<VersionInfo>>;
 
+    /// Fetch only what changed since `cursor`.
+    /// Default implementation refetches the full list via get_versions()
+    async fn fetch_versions_incremental(
+        &self,
+        name: &str,
+        _cursor: &VersionSyncCursor,
+    ) -> CogniaResult<VersionFetch> {
+        Ok(VersionFetch::Full {
+            versions: self.get_versions(name).await?,
+            etag: None,
+            last_modified: None,
+        })
+    }
+
     async fn get_dependencies(&self, _name: &str, _version: &str) -> CogniaResult<Vec<Dependency>> {
         Ok(vec![])
     }
@@ -325,6 +447,47 @@ async fn get_installed_version(&self, name: &str) -> CogniaResult<Option<String>
             .map(|p| p.version))
     }
 
+    /// Verify that a package still works after it was installed or updated
+    /// Default implementation checks the version reported by the provider
+    async fn verify_installed(
+        &self,
+        name: &str,
+        expected_version: &str,
+    ) -> CogniaResult<PackageVerification> {
+        let method = "installed_version";
+        Ok(match self.get_installed_version(name).await? {
+            Some(installed) if versions_match(&installed, expected_version) => {
+                PackageVerification::passed(method)
+            }
+            Some(installed) => PackageVerification::failed(
+                method,
+                format!(
+                    "Expected {} {} but {} is installed",
+                    name, expected_version, installed
+                ),
+            ),
+            None => PackageVerification::failed(
+                method,
+                format!("{} is no longer reported as installed", name),
+            ),
+        })
+    }
+
+    /// Install into `prefix` without touching the provider's normal install
+    /// location, so the package can be tried out and thrown away. Providers
+    /// that can only install system-wide keep this default.
+    async fn install_to_prefix(
+        &self,
+        request: InstallRequest,
+        prefix: &Path,
+    ) -> CogniaResult<InstallReceipt> {
+        let _ = (request, prefix);
+        Err(crate::error::CogniaError::Unsupported(format!(
+            "{} cannot install into an isolated prefix",
+            self.display_name()
+        )))
+    }
+
     async fn uninstall(&self, request: UninstallRequest) -> CogniaResult<()>;
 
     async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<InstalledPackage>>;
@@ -332,6 +495,122 @@ async fn get_installed_version(&self, name: &str) -> CogniaResult<Option<String>
     async fn check_updates(&self, packages: &[String]) -> CogniaResult<Vec<UpdateInfo>>;
 }
 
+/// Fail with [`CogniaError::UnsupportedOperation`](crate::error::CogniaError::UnsupportedOperation)
+/// unless `provider` declares `capability`
+pub fn require_capability(
+    provider: &dyn Provider,
+    capability: Capability,
+    operation: &str,
+) -> CogniaResult<()> {
+    let capabilities = provider.capabilities();
+    if capabilities.contains(&capability) {
+        return Ok(());
+    }
+    let mut declared: Vec<String> = capabilities
+        .into_iter()
+        .map(|c| c.as_str().to_string())
+        .collect();
+    declared.sort();
+    Err(crate::error::CogniaError::UnsupportedOperation {
+        provider: provider.id().to_string(),
+        operation: operation.to_string(),
+        capabilities: declared,
+    })
+}
+
+/// Outcome of a post-install verification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageVerification {
+    pub passed: bool,
+    /// How the package was checked (e.g. `installed_version`, `binary`, `import`)
+    pub method: String,
+    pub message: Option<String>,
+}
+
+impl PackageVerification {
+    pub fn passed(method: &str) -> Self {
+        Self {
+            passed: true,
+            method: method.to_string(),
+            message: None,
+        }
+    }
+
+    pub fn failed(method: &str, message: impl Into<String>) -> Self {
+        Self {
+            passed: false,
+            method: method.to_string(),
+            message: Some(message.into()),
+        }
+    }
+
+    /// Verification could not be performed; treated as passing so that an
+    /// unrelated tooling problem never triggers a rollback
+    pub fn inconclusive(method: &str, message: impl Into<String>) -> Self {
+        Self {
+            passed: true,
+            method: method.to_string(),
+            message: Some(message.into()),
+        }
+    }
+}
+
+/// Compare versions as reported by different tools (`v1.2.0` vs `1.2.0`, `1.2` vs `1.2.0`)
+pub fn versions_match(installed: &str, expected: &str) -> bool {
+    let normalize = |v: &str| {
+        let v = v.trim().trim_start_matches('v');
+        let mut parts: Vec<&str> = v.split('.').collect();
+        while parts.len() > 1 && parts.last() == Some(&"0") {
+            parts.pop();
+        }
+        parts.join(".")
+    };
+    normalize(installed) == normalize(expected)
+}
+
+/// Run each binary with `--version`. Binaries that cannot be launched, that the
+/// loader rejects (exit code 126/127) or that crash fail the verification; any
+/// other exit code passes since not every tool supports `--version`.
+pub async fn verify_binaries_run(binaries: &[PathBuf]) -> PackageVerification {
+    let method = "binary";
+    if binaries.is_empty() {
+        return PackageVerification::inconclusive(method, "No binaries to run");
+    }
+
+    for binary in binaries {
+        let program = binary.to_string_lossy();
+        let opts = crate::platform::process::ProcessOptions::new()
+            .with_timeout(std::time::Duration::from_secs(30));
+        match crate::platform::process::execute(&program, &["--version"], Some(opts)).await {
+            Ok(out) if out.exit_code < 0 || matches!(out.exit_code, 126 | 127) => {
+                let stderr = out.stderr.trim();
+                return PackageVerification::failed(
+                    method,
+                    format!(
+                        "{} exited with code {}{}",
+                        binary.display(),
+                        out.exit_code,
+                        if stderr.is_empty() {
+                            String::new()
+                        } else {
+                            format!(": {}", stderr)
+                        }
+                    ),
+                );
+            }
+            Ok(_) => {}
+            Err(e) => {
+                return PackageVerification::failed(
+                    method,
+                    format!("Failed to run {}: {}", binary.display(), e),
+                );
+            }
+        }
+    }
+
+    PackageVerification::passed(method)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateInfo {
     pub name: String,
@@ -340,6 +619,42 @@ pub struct UpdateInfo {
     pub provider: String,
 }
 
+/// Manager-specific figure reported alongside provider status, such as the
+/// number of scoop buckets or outdated chocolatey packages.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderHealthMetric {
+    pub key: String,
+    pub label: String,
+    pub value: u64,
+}
+
+impl ProviderHealthMetric {
+    pub fn new(key: &str, label: &str, value: u64) -> Self {
+        Self {
+            key: key.to_string(),
+            label: label.to_string(),
+            value,
+        }
+    }
+}
+
+/// Operation names checked with [`SystemPackageProvider::requires_elevation`]
+/// when reporting which operations need admin rights. Managers with extra
+/// operations (sources, pins, global installs) answer for those too.
+pub const ELEVATION_CHECKED_OPERATIONS: &[&str] = &[
+    "install",
+    "uninstall",
+    "upgrade",
+    "update_index",
+    "install_global",
+    "uninstall_global",
+    "cleanup_global",
+    "source_add",
+    "source_remove",
+    "pin_add",
+    "pin_remove",
+];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProviderStatus {
     pub id: String,
@@ -518,6 +833,15 @@ pub trait SystemPackageProvider: Provider {
 
     fn requires_elevation(&self, operation: &str) -> bool;
 
+    /// Entries of [`ELEVATION_CHECKED_OPERATIONS`] that need admin rights
+    fn elevated_operations(&self) -> Vec<String> {
+        ELEVATION_CHECKED_OPERATIONS
+            .iter()
+            .filter(|operation| self.requires_elevation(operation))
+            .map(|operation| operation.to_string())
+            .collect()
+    }
+
     async fn get_provider_status(&self) -> ProviderStatus {
         let installed = self.is_available().await;
         let (version, executable_path) = if installed {
@@ -625,6 +949,78 @@ pub struct ArtifactInfo {
 mod tests {
     use super::*;
 
+    fn listed(versions: &[&str]) -> Vec<VersionInfo> {
+        versions
+            .iter()
+            .map(|version| VersionInfo {
+                version: version.to_string(),
+                release_date: None,
+                deprecated: false,
+                yanked: false,
+            })
+            .collect()
+    }
+
+    fn cursor_of(versions: &[&str]) -> VersionSyncCursor {
+        VersionSyncCursor {
+            known_versions: versions.iter().map(|v| v.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_from_listing_returns_only_new_versions() {
+        let fetch = VersionFetch::from_listing(
+            listed(&["1.2.0", "1.1.0", "1.0.0"]),
+            &cursor_of(&["1.1.0", "1.0.0"]),
+            None,
+            None,
+        );
+        match fetch {
+            VersionFetch::Delta { versions, .. } => {
+                assert_eq!(versions.len(), 1);
+                assert_eq!(versions[0].version, "1.2.0");
+            }
+            other => panic!("expected a delta, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_from_listing_unchanged_when_nothing_new() {
+        let fetch = VersionFetch::from_listing(
+            listed(&["1.1.0", "1.0.0"]),
+            &cursor_of(&["1.1.0", "1.0.0"]),
+            None,
+            None,
+        );
+        assert!(matches!(fetch, VersionFetch::Unchanged));
+    }
+
+    #[test]
+    fn test_from_listing_full_when_a_version_disappears() {
+        let fetch = VersionFetch::from_listing(
+            listed(&["1.2.0", "1.0.0"]),
+            &cursor_of(&["1.1.0", "1.0.0"]),
+            None,
+            None,
+        );
+        match fetch {
+            VersionFetch::Full { versions, .. } => assert_eq!(versions.len(), 2),
+            other => panic!("expected a full listing, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_from_listing_full_without_cursor() {
+        let fetch = VersionFetch::from_listing(
+            listed(&["1.0.0"]),
+            &VersionSyncCursor::default(),
+            Some("\"abc\"".into()),
+            None,
+        );
+        assert!(matches!(fetch, VersionFetch::Full { etag: Some(_), .. }));
+    }
+
     #[test]
     fn test_install_progress_event_new() {
         let evt = InstallProgressEvent::new(InstallStage::Fetching, "test-pkg");
@@ -706,6 +1102,20 @@ fn test_capability_serde_roundtrip() {
         assert_eq!(deserialized, Capability::Install);
     }
 
+    #[test]
+    fn test_capability_as_str_matches_serde() {
+        for cap in [
+            Capability::LockVersion,
+            Capability::Info,
+            Capability::Versions,
+            Capability::RequiresElevation,
+            Capability::SupportsPrefix,
+        ] {
+            let json = serde_json::to_string(&cap).unwrap();
+            assert_eq!(json, format!("\"{}\"", cap.as_str()));
+        }
+    }
+
     #[test]
     fn test_search_options_default() {
         let opts = SearchOptions::default();
@@ -719,4 +1129,28 @@ fn test_installed_filter_default() {
         assert!(!filter.global_only);
         assert!(filter.name_filter.is_none());
     }
+
+    #[test]
+    fn test_versions_match() {
+        assert!(versions_match("1.2.0", "1.2.0"));
+        assert!(versions_match("v1.2.0", "1.2.0"));
+        assert!(versions_match("1.2", "1.2.0"));
+        assert!(!versions_match("1.10", "1.1"));
+        assert!(!versions_match("2.0.0", "1.9.9"));
+    }
+
+    #[test]
+    fn test_package_verification_constructors() {
+        let ok = PackageVerification::passed("binary");
+        assert!(ok.passed);
+        assert!(ok.message.is_none());
+
+        let bad = PackageVerification::failed("import", "No module named 'x'");
+        assert!(!bad.passed);
+        assert_eq!(bad.method, "import");
+
+        let unknown = PackageVerification::inconclusive("import", "python not found");
+        assert!(unknown.passed);
+        assert!(unknown.message.is_some());
+    }
 }
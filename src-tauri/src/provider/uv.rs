@@ -207,6 +207,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::VersionSwitch,
             Capability::MultiVersion,
             Capability::ProjectLocal,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
     fn supported_platforms(&self) -> Vec<Platform> {
@@ -501,6 +503,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                         install_path: site_packages.join(&name),
                         installed_at: String::new(),
                         is_global: true,
+                        kind: None,
                     })
                 })
                 .collect());
@@ -6,6 +6,7 @@
     process::{self, ProcessOptions},
 };
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
@@ -21,12 +22,9 @@ pub struct VoltaProvider {
 
 impl VoltaProvider {
     pub fn new() -> Self {
-        let volta_home = std::env::var("VOLTA_HOME")
-            .ok()
-            .map(PathBuf::from)
-            .or_else(|| dirs_home().map(|h| h.join(".volta")));
-
-        Self { volta_home }
+        Self {
+            volta_home: volta_home_dir(),
+        }
     }
 
     fn volta_home(&self) -> CogniaResult<&PathBuf> {
@@ -82,6 +80,211 @@ fn parse_volta_list(output: &str) -> Vec<(String, String, PathBuf)> {
 
         results
     }
+
+    /// List every toolchain Volta has fetched, marking the user defaults
+    /// recorded in `tools/user/platform.json`.
+    pub async fn list_toolchains(&self) -> CogniaResult<Vec<VoltaToolchain>> {
+        let out = self.run_volta(&["list", "all", "--format=plain"]).await?;
+        let mut toolchains = parse_volta_plain_list(&out);
+
+        if let Ok(home) = self.volta_home() {
+            let platform_json = home.join("tools").join("user").join("platform.json");
+            if let Ok(content) = crate::platform::fs::read_file_string(&platform_json).await {
+                let defaults = parse_volta_platform_json(&content);
+                for toolchain in &mut toolchains {
+                    if defaults.iter().any(|(tool, version)| {
+                        tool == &toolchain.tool && version == &toolchain.version
+                    }) {
+                        toolchain.is_default = true;
+                    }
+                }
+            }
+        }
+
+        Ok(toolchains)
+    }
+
+    /// Fetch a tool and make it the user default (`volta install tool[@version]`)
+    pub async fn install_tool(&self, tool: &str, version: Option<&str>) -> CogniaResult<String> {
+        let spec = volta_tool_spec(tool, version)?;
+        self.run_volta(&["install", &spec]).await
+    }
+
+    /// Pin a tool in the `package.json` of `project_path` (`volta pin tool[@version]`)
+    pub async fn pin_tool(
+        &self,
+        tool: &str,
+        version: Option<&str>,
+        project_path: &Path,
+    ) -> CogniaResult<String> {
+        let spec = volta_tool_spec(tool, version)?;
+        if !project_path.join("package.json").exists() {
+            return Err(CogniaError::Provider(format!(
+                "No package.json found in {}",
+                project_path.display()
+            )));
+        }
+        let cwd = project_path.to_string_lossy().to_string();
+        let opts = ProcessOptions::new()
+            .with_timeout(Duration::from_secs(120))
+            .with_cwd(&cwd);
+        let out = process::execute("volta", &["pin", &spec], Some(opts)).await?;
+        if out.success {
+            Ok(out.stdout)
+        } else {
+            Err(CogniaError::Provider(out.stderr))
+        }
+    }
+
+    /// Resolve the binary Volta would run for `tool` in the current context
+    pub async fn which_tool(&self, tool: &str) -> CogniaResult<Option<PathBuf>> {
+        if tool.trim().is_empty() {
+            return Err(CogniaError::Provider("Tool name is required".into()));
+        }
+        let opts = ProcessOptions::new().with_timeout(Duration::from_secs(30));
+        let out = process::execute("volta", &["which", tool.trim()], Some(opts)).await?;
+        let path = out.stdout.trim();
+        if out.success && !path.is_empty() {
+            Ok(Some(PathBuf::from(path)))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// A toolchain entry reported by `volta list all --format=plain`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VoltaToolchain {
+    /// `runtime`, `package-manager` or `package`
+    pub kind: String,
+    pub tool: String,
+    pub version: String,
+    pub is_default: bool,
+    /// `package.json` that pins this version for the current directory
+    pub pinned_by: Option<String>,
+}
+
+/// Resolve Volta's home directory (`VOLTA_HOME`, falling back to `~/.volta`)
+pub fn volta_home_dir() -> Option<PathBuf> {
+    std::env::var("VOLTA_HOME")
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        .map(PathBuf::from)
+        .or_else(|| dirs_home().map(|h| h.join(".volta")))
+}
+
+/// Directory holding Volta's shims (`$VOLTA_HOME/bin`)
+pub fn volta_bin_dir() -> Option<PathBuf> {
+    volta_home_dir().map(|home| home.join("bin"))
+}
+
+/// Whether `path` (a binary or directory) lives directly in Volta's shim directory
+pub fn is_in_volta_bin(path: &Path, volta_bin: &Path) -> bool {
+    let normalize = |p: &Path| {
+        let s = p.to_string_lossy().replace('\\', "/");
+        let s = s.trim_end_matches('/').to_string();
+        if cfg!(windows) {
+            s.to_ascii_lowercase()
+        } else {
+            s
+        }
+    };
+    let bin = normalize(volta_bin);
+    let target = normalize(path);
+    target == bin || path.parent().is_some_and(|parent| normalize(parent) == bin)
+}
+
+/// Whether the `tool` resolved from PATH is one of Volta's shims
+pub async fn is_volta_managed(tool: &str) -> bool {
+    let Some(bin) = volta_bin_dir() else {
+        return false;
+    };
+    match process::which(tool).await {
+        Some(path) => is_in_volta_bin(Path::new(&path), &bin),
+        None => false,
+    }
+}
+
+fn volta_tool_spec(tool: &str, version: Option<&str>) -> CogniaResult<String> {
+    let tool = tool.trim();
+    if tool.is_empty() {
+        return Err(CogniaError::Provider("Tool name is required".into()));
+    }
+    Ok(match version.map(str::trim).filter(|v| !v.is_empty()) {
+        Some(version) => format!("{}@{}", tool, version),
+        None => tool.to_string(),
+    })
+}
+
+/// Parse `volta list all --format=plain`, e.g.
+/// `runtime node@20.11.0 (default)` or
+/// `package typescript@5.4.2 / tsc, tsserver / node@20.11.0 npm@built-in (default)`.
+pub fn parse_volta_plain_list(output: &str) -> Vec<VoltaToolchain> {
+    let mut toolchains = Vec::new();
+
+    for line in output.lines() {
+        let line = line.trim();
+        let Some((kind, rest)) = line.split_once(' ') else {
+            continue;
+        };
+        if !matches!(kind, "runtime" | "package-manager" | "package") {
+            continue;
+        }
+
+        let (spec, status) = match rest.rfind(" (") {
+            Some(idx) if rest.ends_with(')') => {
+                (&rest[..idx], Some(&rest[idx + 2..rest.len() - 1]))
+            }
+            _ => (rest, None),
+        };
+        // Packages carry their binaries and platform after ` / `
+        let spec = spec.split(" / ").next().unwrap_or(spec).trim();
+
+        let (tool, version) = split_name_version(spec);
+        let Some(version) = version else {
+            continue;
+        };
+        if tool.is_empty() {
+            continue;
+        }
+
+        let pinned_by = status
+            .and_then(|s| s.strip_prefix("current @ "))
+            .map(|p| p.trim().to_string());
+
+        toolchains.push(VoltaToolchain {
+            kind: kind.to_string(),
+            tool: tool.to_string(),
+            version: version.trim_start_matches('v').to_string(),
+            is_default: status == Some("default"),
+            pinned_by,
+        });
+    }
+
+    toolchains
+}
+
+/// Parse the user default platform (`$VOLTA_HOME/tools/user/platform.json`)
+/// into `(tool, version)` pairs.
+pub fn parse_volta_platform_json(content: &str) -> Vec<(String, String)> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+
+    let mut defaults = Vec::new();
+    if let Some(runtime) = json["node"]["runtime"].as_str() {
+        defaults.push(("node".to_string(), runtime.to_string()));
+    }
+    if let Some(npm) = json["node"]["npm"].as_str() {
+        defaults.push(("npm".to_string(), npm.to_string()));
+    }
+    for tool in ["pnpm", "yarn"] {
+        if let Some(version) = json[tool].as_str() {
+            defaults.push((tool.to_string(), version.to_string()));
+        }
+    }
+    defaults
 }
 
 impl Default for VoltaProvider {
@@ -105,6 +308,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::List,
             Capability::VersionSwitch,
             Capability::Search,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
     fn supported_platforms(&self) -> Vec<Platform> {
@@ -324,6 +529,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                     .join(&version),
                 installed_at: String::new(),
                 is_global: true,
+                kind: None,
             })
             .collect())
     }
@@ -561,4 +767,75 @@ fn test_version_file_name() {
     fn test_default_impl() {
         let _provider = VoltaProvider::default();
     }
+
+    #[test]
+    fn test_parse_volta_plain_list() {
+        let output = "runtime node@20.11.0 (default)\n\
+runtime node@18.19.0 (current @ /work/app/package.json)\n\
+package-manager yarn@1.22.19\n\
+package typescript@5.4.2 / tsc, tsserver / node@20.11.0 npm@built-in (default)\n\
+package @scope/cli@2.0.0 / scli / node@20.11.0 npm@built-in (default)\n\
+⚡️ unexpected banner\n";
+        let result = parse_volta_plain_list(output);
+        assert_eq!(result.len(), 5);
+
+        assert_eq!(result[0].kind, "runtime");
+        assert_eq!(result[0].tool, "node");
+        assert_eq!(result[0].version, "20.11.0");
+        assert!(result[0].is_default);
+        assert!(result[0].pinned_by.is_none());
+
+        assert!(!result[1].is_default);
+        assert_eq!(
+            result[1].pinned_by.as_deref(),
+            Some("/work/app/package.json")
+        );
+
+        assert_eq!(result[2].kind, "package-manager");
+        assert_eq!(result[2].tool, "yarn");
+        assert!(!result[2].is_default);
+
+        assert_eq!(result[3].tool, "typescript");
+        assert_eq!(result[3].version, "5.4.2");
+        assert!(result[3].is_default);
+
+        assert_eq!(result[4].tool, "@scope/cli");
+        assert_eq!(result[4].version, "2.0.0");
+    }
+
+    #[test]
+    fn test_parse_volta_platform_json() {
+        let content =
+            r#"{"node":{"runtime":"20.11.0","npm":"10.2.4"},"pnpm":null,"yarn":"1.22.19"}"#;
+        let defaults = parse_volta_platform_json(content);
+        assert_eq!(
+            defaults,
+            vec![
+                ("node".to_string(), "20.11.0".to_string()),
+                ("npm".to_string(), "10.2.4".to_string()),
+                ("yarn".to_string(), "1.22.19".to_string()),
+            ]
+        );
+        assert!(parse_volta_platform_json("not json").is_empty());
+    }
+
+    #[test]
+    fn test_volta_tool_spec() {
+        assert_eq!(volta_tool_spec("node", Some("20")).unwrap(), "node@20");
+        assert_eq!(volta_tool_spec(" yarn ", Some(" ")).unwrap(), "yarn");
+        assert_eq!(volta_tool_spec("pnpm", None).unwrap(), "pnpm");
+        assert!(volta_tool_spec("  ", Some("1.0.0")).is_err());
+    }
+
+    #[test]
+    fn test_is_in_volta_bin() {
+        let bin = Path::new("/home/u/.volta/bin");
+        assert!(is_in_volta_bin(Path::new("/home/u/.volta/bin/node"), bin));
+        assert!(is_in_volta_bin(Path::new("/home/u/.volta/bin/"), bin));
+        assert!(!is_in_volta_bin(
+            Path::new("/home/u/.volta/tools/image/node/20.11.0/bin/node"),
+            bin
+        ));
+        assert!(!is_in_volta_bin(Path::new("/usr/local/bin/node"), bin));
+    }
 }
@@ -3,7 +3,7 @@
 use crate::platform::{env::Platform, process};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -48,6 +48,218 @@ pub struct WingetInfo {
     pub sources: Vec<WingetSource>,
 }
 
+/// Source that publishes the packages in [`WINGET_ENV_PACKAGES`]
+pub const WINGET_COMMUNITY_SOURCE: &str = "winget";
+
+/// Winget package used to install an environment type.
+#[derive(Debug, Clone, Copy)]
+pub struct WingetEnvPackage {
+    pub env_type: &'static str,
+    /// Package installed when no version is requested
+    pub default_id: &'static str,
+    /// Package installed when a version is requested. `{channel}` is
+    /// replaced by the first `channel_parts` components of the version.
+    pub versioned_id: &'static str,
+    pub channel_parts: usize,
+    /// Version keywords that select a package of their own
+    pub keywords: &'static [(&'static str, &'static str)],
+}
+
+/// Built-in env type → winget package table. Entries can be overridden or
+/// added with the `providers.winget.env_packages` setting.
+pub const WINGET_ENV_PACKAGES: &[WingetEnvPackage] = &[
+    WingetEnvPackage {
+        env_type: "python",
+        default_id: "Python.Python.3.12",
+        versioned_id: "Python.Python.{channel}",
+        channel_parts: 2,
+        keywords: &[],
+    },
+    WingetEnvPackage {
+        env_type: "node",
+        default_id: "OpenJS.NodeJS.LTS",
+        versioned_id: "OpenJS.NodeJS",
+        channel_parts: 0,
+        keywords: &[("lts", "OpenJS.NodeJS.LTS"), ("current", "OpenJS.NodeJS")],
+    },
+    WingetEnvPackage {
+        env_type: "git",
+        default_id: "Git.Git",
+        versioned_id: "Git.Git",
+        channel_parts: 0,
+        keywords: &[],
+    },
+    WingetEnvPackage {
+        env_type: "go",
+        default_id: "GoLang.Go",
+        versioned_id: "GoLang.Go",
+        channel_parts: 0,
+        keywords: &[],
+    },
+    WingetEnvPackage {
+        env_type: "rust",
+        default_id: "Rustlang.Rustup",
+        versioned_id: "Rustlang.Rustup",
+        channel_parts: 0,
+        keywords: &[],
+    },
+    WingetEnvPackage {
+        env_type: "java",
+        default_id: "EclipseAdoptium.Temurin.21.JDK",
+        versioned_id: "EclipseAdoptium.Temurin.{channel}.JDK",
+        channel_parts: 1,
+        keywords: &[],
+    },
+    WingetEnvPackage {
+        env_type: "dotnet",
+        default_id: "Microsoft.DotNet.SDK.8",
+        versioned_id: "Microsoft.DotNet.SDK.{channel}",
+        channel_parts: 1,
+        keywords: &[],
+    },
+    WingetEnvPackage {
+        env_type: "ruby",
+        default_id: "RubyInstallerTeam.Ruby.3.3",
+        versioned_id: "RubyInstallerTeam.Ruby.{channel}",
+        channel_parts: 2,
+        keywords: &[],
+    },
+    WingetEnvPackage {
+        env_type: "deno",
+        default_id: "DenoLand.Deno",
+        versioned_id: "DenoLand.Deno",
+        channel_parts: 0,
+        keywords: &[],
+    },
+    WingetEnvPackage {
+        env_type: "bun",
+        default_id: "Oven-sh.Bun",
+        versioned_id: "Oven-sh.Bun",
+        channel_parts: 0,
+        keywords: &[],
+    },
+];
+
+/// Package picked for an environment install.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WingetEnvSelection {
+    pub env_type: String,
+    pub package_id: String,
+    /// Exact or partial version still to resolve against the package's
+    /// versions; `None` installs its latest release
+    pub version: Option<String>,
+}
+
+fn canonical_env_type(env_type: &str) -> String {
+    let lower = env_type.trim().to_ascii_lowercase();
+    match lower.as_str() {
+        "nodejs" => "node".into(),
+        "python3" => "python".into(),
+        "golang" => "go".into(),
+        ".net" => "dotnet".into(),
+        _ => lower,
+    }
+}
+
+/// Pick the winget package for `env_type`. `overrides` (env type → package
+/// ID) take precedence over [`WINGET_ENV_PACKAGES`].
+pub fn select_env_package(
+    env_type: &str,
+    version: Option<&str>,
+    overrides: &HashMap<String, String>,
+) -> Option<WingetEnvSelection> {
+    let env_type = canonical_env_type(env_type);
+    let version = version
+        .map(str::trim)
+        .filter(|v| !v.is_empty() && !v.eq_ignore_ascii_case("latest"));
+    let selection = |package_id: &str, version: Option<&str>| WingetEnvSelection {
+        env_type: env_type.clone(),
+        package_id: package_id.to_string(),
+        version: version.map(str::to_string),
+    };
+
+    if let Some(id) = overrides.get(&env_type) {
+        return Some(selection(id, version));
+    }
+
+    let package = WINGET_ENV_PACKAGES
+        .iter()
+        .find(|package| package.env_type == env_type)?;
+    let Some(version) = version else {
+        return Some(selection(package.default_id, None));
+    };
+    if let Some((_, id)) = package
+        .keywords
+        .iter()
+        .find(|(keyword, _)| version.eq_ignore_ascii_case(keyword))
+    {
+        return Some(selection(id, None));
+    }
+
+    let parts: Vec<&str> = version.split('.').collect();
+    if package.channel_parts == 0 || parts.len() < package.channel_parts {
+        let id = if package.channel_parts == 0 {
+            package.versioned_id
+        } else {
+            package.default_id
+        };
+        return Some(selection(id, Some(version)));
+    }
+
+    // The channel package only carries releases of that line, so a version
+    // naming just the channel installs its latest release
+    let channel = parts[..package.channel_parts].join(".");
+    let id = package.versioned_id.replace("{channel}", &channel);
+    let rest = (parts.len() > package.channel_parts).then_some(version);
+    Some(selection(&id, rest))
+}
+
+fn version_key(version: &str) -> Vec<u64> {
+    version
+        .split(|c: char| !c.is_ascii_digit())
+        .filter_map(|part| part.parse().ok())
+        .collect()
+}
+
+/// Resolve a requested version against a package's available versions: an
+/// exact match, else the newest release of the requested line (`20` → `20.x.y`).
+pub fn match_available_version(available: &[String], requested: &str) -> Option<String> {
+    if let Some(exact) = available.iter().find(|v| v.as_str() == requested) {
+        return Some(exact.clone());
+    }
+    let prefix = format!("{}.", requested);
+    available
+        .iter()
+        .filter(|v| v.starts_with(&prefix))
+        .max_by(|a, b| version_key(a).cmp(&version_key(b)))
+        .cloned()
+}
+
+/// Explain why installs from the community source would fail, given the
+/// enabled sources.
+pub fn community_source_problem(sources: &[WingetSource]) -> Option<String> {
+    if sources
+        .iter()
+        .any(|source| source.name.eq_ignore_ascii_case(WINGET_COMMUNITY_SOURCE))
+    {
+        return None;
+    }
+    if sources
+        .iter()
+        .any(|source| source.name.eq_ignore_ascii_case("msstore"))
+    {
+        return Some(
+            "Only the msstore source is enabled, but toolchains are published on the `winget` \
+             source. Re-enable it with `winget source reset --force`."
+                .into(),
+        );
+    }
+    Some(
+        "No winget sources are enabled. Restore the defaults with `winget source reset --force`."
+            .into(),
+    )
+}
+
 impl WingetProvider {
     pub fn new() -> Self {
         Self
@@ -434,6 +646,37 @@ pub async fn get_info(&self) -> CogniaResult<WingetInfo> {
         })
     }
 
+    // ── Source-pinned install ────────────────────────────────────────────
+
+    /// Install an exact package ID from one source, so a same-named msstore
+    /// listing can't be picked instead.
+    pub async fn install_from_source(
+        &self,
+        id: &str,
+        version: Option<&str>,
+        source: &str,
+    ) -> CogniaResult<String> {
+        let mut args = vec![
+            "install",
+            "--id",
+            id,
+            "--exact",
+            "--source",
+            source,
+            "--accept-package-agreements",
+            "--silent",
+        ];
+        if let Some(v) = version {
+            args.extend(&["--version", v]);
+        }
+        self.run_winget_long(&args).await
+    }
+
+    /// Installed version of `id`, if installed.
+    pub async fn installed_version(&self, id: &str) -> Option<String> {
+        self.query_installed_version(id).await.ok()
+    }
+
     // ── Advanced install with scope/arch/locale ─────────────────────────
 
     /// Install a package with advanced options (scope, architecture, locale, location).
@@ -501,6 +744,9 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::Upgrade,
             Capability::UpdateIndex,
             Capability::LockVersion,
+            Capability::Info,
+            Capability::Versions,
+            Capability::RequiresElevation,
         ])
     }
     fn supported_platforms(&self) -> Vec<Platform> {
@@ -797,6 +1043,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                     install_path: PathBuf::new(),
                     installed_at: String::new(),
                     is_global: true,
+                    kind: None,
                 })
             })
             .collect())
@@ -1205,6 +1452,94 @@ fn test_winget_source_serde() {
         assert_eq!(deserialized.name, "winget");
     }
 
+    #[test]
+    fn test_select_env_package_defaults() {
+        let none = HashMap::new();
+        let python = select_env_package("python", None, &none).unwrap();
+        assert_eq!(python.package_id, "Python.Python.3.12");
+        assert_eq!(python.version, None);
+
+        let node = select_env_package("nodejs", Some("latest"), &none).unwrap();
+        assert_eq!(node.env_type, "node");
+        assert_eq!(node.package_id, "OpenJS.NodeJS.LTS");
+
+        assert_eq!(
+            select_env_package("git", None, &none).unwrap().package_id,
+            "Git.Git"
+        );
+        assert!(select_env_package("cobol", None, &none).is_none());
+    }
+
+    #[test]
+    fn test_select_env_package_version_channels() {
+        let none = HashMap::new();
+        let channel = select_env_package("python", Some("3.11"), &none).unwrap();
+        assert_eq!(channel.package_id, "Python.Python.3.11");
+        assert_eq!(channel.version, None);
+
+        let exact = select_env_package("python", Some("3.11.9"), &none).unwrap();
+        assert_eq!(exact.package_id, "Python.Python.3.11");
+        assert_eq!(exact.version.as_deref(), Some("3.11.9"));
+
+        let java = select_env_package("java", Some("17"), &none).unwrap();
+        assert_eq!(java.package_id, "EclipseAdoptium.Temurin.17.JDK");
+
+        let lts = select_env_package("node", Some("LTS"), &none).unwrap();
+        assert_eq!(lts.package_id, "OpenJS.NodeJS.LTS");
+        let node = select_env_package("node", Some("20"), &none).unwrap();
+        assert_eq!(node.package_id, "OpenJS.NodeJS");
+        assert_eq!(node.version.as_deref(), Some("20"));
+    }
+
+    #[test]
+    fn test_select_env_package_overrides() {
+        let overrides = HashMap::from([
+            ("python".to_string(), "Python.Python.3.13".to_string()),
+            ("cmake".to_string(), "Kitware.CMake".to_string()),
+        ]);
+        let python = select_env_package("python", Some("3.13.1"), &overrides).unwrap();
+        assert_eq!(python.package_id, "Python.Python.3.13");
+        assert_eq!(python.version.as_deref(), Some("3.13.1"));
+        assert_eq!(
+            select_env_package("cmake", None, &overrides)
+                .unwrap()
+                .package_id,
+            "Kitware.CMake"
+        );
+    }
+
+    #[test]
+    fn test_match_available_version() {
+        let available: Vec<String> = ["20.9.0", "20.11.1", "20.10.0", "18.19.0", "2.0.0"]
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        assert_eq!(
+            match_available_version(&available, "20").as_deref(),
+            Some("20.11.1")
+        );
+        assert_eq!(
+            match_available_version(&available, "18.19.0").as_deref(),
+            Some("18.19.0")
+        );
+        assert_eq!(match_available_version(&available, "16"), None);
+    }
+
+    #[test]
+    fn test_community_source_problem() {
+        let source = |name: &str| WingetSource {
+            name: name.into(),
+            argument: String::new(),
+            source_type: String::new(),
+            updated: String::new(),
+        };
+        assert!(community_source_problem(&[source("winget"), source("msstore")]).is_none());
+        assert!(community_source_problem(&[source("msstore")])
+            .unwrap()
+            .contains("msstore"));
+        assert!(community_source_problem(&[]).is_some());
+    }
+
     #[test]
     fn test_winget_info_serde() {
         let info = WingetInfo {
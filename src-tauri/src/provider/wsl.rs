@@ -6,7 +6,9 @@
 use std::collections::HashSet;
 use std::path::PathBuf;
 use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
+use tokio::io::AsyncReadExt;
 use tokio::process::Command as TokioCommand;
 
 /// Default timeout for WSL commands (120 seconds)
@@ -15,6 +17,10 @@
 const WSL_LONG_TIMEOUT: Duration = Duration::from_secs(600);
 /// Short timeout for availability checks
 const WSL_AVAIL_TIMEOUT: Duration = Duration::from_secs(15);
+/// Output kept inline in a batch result; the full text goes to a spill file
+pub const WSL_BATCH_OUTPUT_LIMIT: usize = 16 * 1024;
+/// Delimiter between `/etc/os-release` and the probed package manager
+const PM_PROBE_DELIM: &str = "---COGNIA_DELIM---";
 
 /// Decode bytes that may be UTF-16LE (common for wsl.exe on Windows) or UTF-8.
 ///
@@ -95,10 +101,39 @@ fn decode_utf16be(bytes: &[u8]) -> String {
 /// This function reads raw bytes and decodes them properly instead of using
 /// the standard UTF-8 assumption in `process::execute`.
 async fn execute_wsl(args: &[&str], timeout: Duration) -> CogniaResult<ProcessOutput> {
+    execute_wsl_cancellable(args, timeout, None).await
+}
+
+/// Like [`execute_wsl`], but `wsl.exe` is killed as soon as `cancel` is set.
+async fn execute_wsl_cancellable(
+    args: &[&str],
+    timeout: Duration,
+    cancel: Option<&AtomicBool>,
+) -> CogniaResult<ProcessOutput> {
+    run_wsl(args, timeout, cancel)
+        .await?
+        .ok_or_else(|| wsl_timed_out(timeout))
+}
+
+fn wsl_timed_out(timeout: Duration) -> CogniaError {
+    CogniaError::Provider(format!("WSL command timed out after {:?}", timeout))
+}
+
+/// Run wsl.exe to completion, or `None` once `timeout` passes. On timeout or
+/// cancel wsl.exe is killed and reaped before this returns.
+async fn run_wsl(
+    args: &[&str],
+    timeout: Duration,
+    cancel: Option<&AtomicBool>,
+) -> CogniaResult<Option<ProcessOutput>> {
+    const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
     let mut cmd = TokioCommand::new("wsl.exe");
     cmd.args(args);
     cmd.stdout(Stdio::piped());
     cmd.stderr(Stdio::piped());
+    // Backstop for when this future itself is dropped mid-run
+    cmd.kill_on_drop(true);
 
     // Prevent console window flash on Windows
     #[cfg(windows)]
@@ -107,26 +142,59 @@ async fn execute_wsl(args: &[&str], timeout: Duration) -> CogniaResult<ProcessOu
         cmd.creation_flags(CREATE_NO_WINDOW);
     }
 
-    let child = cmd
+    let mut child = cmd
         .spawn()
         .map_err(|e| CogniaError::Provider(format!("Failed to start wsl.exe: {}", e)))?;
-
-    let output = tokio::time::timeout(timeout, child.wait_with_output())
-        .await
-        .map_err(|_| CogniaError::Provider(format!("WSL command timed out after {:?}", timeout)))?
-        .map_err(|e| CogniaError::Provider(format!("WSL process error: {}", e)))?;
-
-    let stdout = decode_wsl_bytes(&output.stdout);
-    let stderr = decode_wsl_bytes(&output.stderr);
-    let exit_code = output.status.code().unwrap_or(-1);
-    let success = output.status.success();
-
-    Ok(ProcessOutput {
-        exit_code,
-        stdout,
-        stderr,
-        success,
-    })
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let mut stderr = child.stderr.take().expect("stderr is piped");
+
+    let cancelled = async {
+        match cancel {
+            Some(cancel) => {
+                while !cancel.load(Ordering::SeqCst) {
+                    tokio::time::sleep(CANCEL_POLL_INTERVAL).await;
+                }
+            }
+            None => std::future::pending::<()>().await,
+        }
+    };
+    let finished = async {
+        let mut out = Vec::new();
+        let mut err = Vec::new();
+        let (status, _, _) = tokio::try_join!(
+            child.wait(),
+            stdout.read_to_end(&mut out),
+            stderr.read_to_end(&mut err)
+        )?;
+        Ok::<_, std::io::Error>((status, out, err))
+    };
+    let mut timed_out = false;
+    let finished = tokio::select! {
+        finished = tokio::time::timeout(timeout, finished) => {
+            timed_out = finished.is_err();
+            finished.ok()
+        }
+        _ = cancelled => None,
+    };
+    let Some(finished) = finished else {
+        if let Err(e) = child.kill().await {
+            log::warn!("Failed to kill wsl.exe: {}", e);
+        }
+        return if timed_out {
+            Ok(None)
+        } else {
+            Err(CogniaError::Cancelled)
+        };
+    };
+    let (status, out, err) =
+        finished.map_err(|e| CogniaError::Provider(format!("WSL process error: {}", e)))?;
+
+    Ok(Some(ProcessOutput {
+        exit_code: status.code().unwrap_or(-1),
+        stdout: decode_wsl_bytes(&out),
+        stderr: decode_wsl_bytes(&err),
+        success: status.success(),
+    }))
 }
 
 /// Execute a non-wsl program (e.g., distro launchers like ubuntu.exe) with standard encoding.
@@ -745,7 +813,10 @@ pub async fn run_wsl(&self, args: &[&str]) -> CogniaResult<String> {
 
     /// Execute a wsl.exe command with a long timeout (for install/export/import).
     pub async fn run_wsl_long(&self, args: &[&str]) -> CogniaResult<String> {
-        let out = execute_wsl(args, WSL_LONG_TIMEOUT).await?;
+        Self::long_output(execute_wsl(args, WSL_LONG_TIMEOUT).await?)
+    }
+
+    fn long_output(out: ProcessOutput) -> CogniaResult<String> {
         if out.success {
             Ok(Self::trim_output(&out.stdout))
         } else {
@@ -1351,6 +1422,233 @@ pub async fn batch_terminate(&self, names: &[String]) -> Vec<(String, bool, Stri
         futures::future::join_all(futures).await
     }
 
+    /// Run `command` in each distro, concurrently or one after another.
+    /// A failure in one distro never stops the others.
+    pub async fn batch_exec(
+        &self,
+        distros: &[String],
+        command: &str,
+        parallel: bool,
+        options: &WslBatchOptions,
+    ) -> Vec<WslBatchItemResult> {
+        self.run_batch(distros, Some(command), parallel, options)
+            .await
+    }
+
+    /// Refresh the package index and install upgrades in each distro with its
+    /// own package manager, all distros concurrently.
+    pub async fn batch_update(
+        &self,
+        distros: &[String],
+        options: &WslBatchOptions,
+    ) -> Vec<WslBatchItemResult> {
+        self.run_batch(distros, None, true, options).await
+    }
+
+    /// Export each distro to a timestamped tar in `dest_dir`, one at a time so
+    /// the exports do not compete for disk. `on_progress` is called before and
+    /// after each distro; once `is_cancelled` returns true the rest are skipped.
+    pub async fn batch_export(
+        &self,
+        distros: &[String],
+        dest_dir: &str,
+        on_progress: impl Fn(WslBatchProgress),
+        is_cancelled: impl Fn() -> bool,
+    ) -> Vec<WslBatchItemResult> {
+        let total = distros.len();
+        let mut results = Vec::with_capacity(total);
+        for distro in distros {
+            let started = std::time::Instant::now();
+            let mut result = WslBatchItemResult::new(distro);
+            if is_cancelled() {
+                result.status = WslBatchStatus::Skipped;
+                result.error = Some("Cancelled".into());
+                results.push(result);
+                continue;
+            }
+            on_progress(WslBatchProgress {
+                distro: distro.clone(),
+                completed: results.len(),
+                total,
+                status: None,
+            });
+            match self.backup_distro(distro, dest_dir).await {
+                Ok(entry) => {
+                    result.status = WslBatchStatus::Succeeded;
+                    result.file_path = Some(entry.file_path);
+                }
+                Err(e) => result.error = Some(e.to_string()),
+            }
+            result.duration_ms = started.elapsed().as_millis() as u64;
+            on_progress(WslBatchProgress {
+                distro: distro.clone(),
+                completed: results.len() + 1,
+                total,
+                status: Some(result.status),
+            });
+            results.push(result);
+        }
+        results
+    }
+
+    /// `command` of `None` runs the package update
+    async fn run_batch(
+        &self,
+        distros: &[String],
+        command: Option<&str>,
+        parallel: bool,
+        options: &WslBatchOptions,
+    ) -> Vec<WslBatchItemResult> {
+        let running = self.list_running().await.unwrap_or_default();
+        let is_running = |name: &str| running.iter().any(|r| r.eq_ignore_ascii_case(name));
+        if parallel {
+            let futures: Vec<_> = distros
+                .iter()
+                .map(|distro| self.batch_item(distro, is_running(distro), command, options))
+                .collect();
+            futures::future::join_all(futures).await
+        } else {
+            let mut results = Vec::with_capacity(distros.len());
+            for distro in distros {
+                results.push(
+                    self.batch_item(distro, is_running(distro), command, options)
+                        .await,
+                );
+            }
+            results
+        }
+    }
+
+    async fn batch_item(
+        &self,
+        distro: &str,
+        running: bool,
+        command: Option<&str>,
+        options: &WslBatchOptions,
+    ) -> WslBatchItemResult {
+        let started = std::time::Instant::now();
+        let mut result = WslBatchItemResult::new(distro);
+
+        if !running {
+            if !options.auto_start {
+                result.status = WslBatchStatus::Skipped;
+                result.error = Some("Distribution is stopped and auto-start is off".into());
+                return result;
+            }
+            if let Err(e) = self.launch_distro(distro, None).await {
+                result.error = Some(format!("Failed to start distribution: {}", e));
+                result.duration_ms = started.elapsed().as_millis() as u64;
+                return result;
+            }
+            result.auto_started = true;
+        }
+
+        let script = match command {
+            Some(command) => Ok((command.to_string(), options.user.clone())),
+            None => self.batch_update_script(distro).await.map(|(pm, script)| {
+                result.package_manager = Some(pm);
+                (script, Some("root".to_string()))
+            }),
+        };
+        match script {
+            Err(e) => result.error = Some(e.to_string()),
+            Ok((script, user)) => {
+                let exec = self
+                    .exec_command_until(distro, &script, user.as_deref(), options.timeout)
+                    .await;
+                match exec {
+                    Ok(None) => {
+                        result.status = WslBatchStatus::TimedOut;
+                        result.error =
+                            Some(format!("Timed out after {}s", options.timeout.as_secs()));
+                    }
+                    Err(e) => result.error = Some(e.to_string()),
+                    Ok(Some((stdout, stderr, exit_code))) => {
+                        result.exit_code = Some(exit_code);
+                        if exit_code == 0 {
+                            result.status = WslBatchStatus::Succeeded;
+                        }
+                        let output = match (stdout.trim().is_empty(), stderr.trim().is_empty()) {
+                            (_, true) => stdout,
+                            (true, false) => stderr,
+                            (false, false) => format!("{}\n{}", stdout, stderr),
+                        };
+                        let kind = if command.is_some() { "exec" } else { "update" };
+                        result.set_output(&output, &options.spill_dir, kind).await;
+                    }
+                }
+            }
+        }
+
+        // Leave the distro stopped again if we started it
+        if result.auto_started {
+            if let Err(e) = self.terminate_distro(distro).await {
+                log::warn!("Failed to stop {} after batch run: {}", distro, e);
+            }
+        }
+        result.duration_ms = started.elapsed().as_millis() as u64;
+        result
+    }
+
+    /// Package manager of `distro` and a script that refreshes and upgrades with it
+    async fn batch_update_script(&self, distro: &str) -> CogniaResult<(String, String)> {
+        let probe = format!(
+            concat!(
+                "cat /etc/os-release 2>/dev/null; echo '{d}'",
+                "; for pm in apt pacman dnf yum zypper apk xbps-install nix emerge swupd; do",
+                "   if command -v $pm >/dev/null 2>&1; then echo $pm; break; fi;",
+                " done"
+            ),
+            d = PM_PROBE_DELIM
+        );
+        let (stdout, _, _) = self.exec_command(distro, &probe, None).await?;
+        let pm = Self::package_manager_from_probe(&stdout).ok_or_else(|| {
+            CogniaError::Provider(format!("No package manager found in '{}'", distro))
+        })?;
+        let script = Self::batch_update_command(&pm).ok_or_else(|| {
+            CogniaError::Provider(format!(
+                "No update commands known for package manager '{}'",
+                pm
+            ))
+        })?;
+        Ok((pm, script))
+    }
+
+    /// Package manager from the batch probe output: the `/etc/os-release` ID
+    /// mapping first, then the first binary found, as in environment detection
+    pub fn package_manager_from_probe(output: &str) -> Option<String> {
+        let (os_release, probed) = output.split_once(PM_PROBE_DELIM).unwrap_or((output, ""));
+        let os_release = Self::parse_os_release(os_release.trim());
+        let id = os_release.get("ID").map(String::as_str).unwrap_or("");
+        let id_like: Vec<String> = os_release
+            .get("ID_LIKE")
+            .map(|s| s.split_whitespace().map(str::to_string).collect())
+            .unwrap_or_default();
+        Self::detect_package_manager_from_id(id, &id_like)
+            .map(str::to_string)
+            .or_else(|| {
+                probed
+                    .lines()
+                    .map(str::trim)
+                    .find(|l| !l.is_empty())
+                    .map(str::to_string)
+            })
+    }
+
+    /// Non-interactive refresh followed by upgrade. The refresh exit code is
+    /// ignored because some managers (e.g. `dnf check-update`) exit non-zero
+    /// when updates are available.
+    pub fn batch_update_command(pm: &str) -> Option<String> {
+        let (update, upgrade) = Self::get_distro_update_command(pm)?;
+        Some(match pm {
+            "apt" => format!(
+                "export DEBIAN_FRONTEND=noninteractive; {}; {}",
+                update, upgrade
+            ),
+            _ => format!("{}; {}", update, upgrade),
+        })
+    }
+
     // ========================================================================
     // Distro health check
     // ========================================================================
@@ -1503,14 +1801,17 @@ pub async fn backup_distro(&self, name: &str, dest_dir: &str) -> CogniaResult<Ws
         let dest_path = PathBuf::from(dest_dir).join(&filename);
 
         // Ensure destination directory exists
-        std::fs::create_dir_all(dest_dir).map_err(|e| {
+        tokio::fs::create_dir_all(dest_dir).await.map_err(|e| {
             CogniaError::Provider(format!("Failed to create backup directory: {}", e))
         })?;
 
         self.export_distro(name, &dest_path.to_string_lossy(), false)
             .await?;
 
-        let size = std::fs::metadata(&dest_path).map(|m| m.len()).unwrap_or(0);
+        let size = tokio::fs::metadata(&dest_path)
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0);
 
         Ok(WslBackupEntry {
             file_name: filename,
@@ -1728,13 +2029,32 @@ pub async fn export_distro(
         name: &str,
         file_path: &str,
         as_vhd: bool,
+    ) -> CogniaResult<()> {
+        self.export_distro_cancellable(name, file_path, as_vhd, None)
+            .await
+    }
+
+    /// Export a distribution, stopping `wsl.exe` once `cancel` is set. The
+    /// partial file of a cancelled export is removed.
+    pub async fn export_distro_cancellable(
+        &self,
+        name: &str,
+        file_path: &str,
+        as_vhd: bool,
+        cancel: Option<&AtomicBool>,
     ) -> CogniaResult<()> {
         let mut args = vec!["--export", name, file_path];
         if as_vhd {
             args.push("--vhd");
         }
-        self.run_wsl_long(&args).await?;
-        Ok(())
+        match execute_wsl_cancellable(&args, WSL_LONG_TIMEOUT, cancel).await {
+            Ok(out) => Self::long_output(out).map(|_| ()),
+            Err(CogniaError::Cancelled) => {
+                let _ = tokio::fs::remove_file(file_path).await;
+                Err(CogniaError::Cancelled)
+            }
+            Err(e) => Err(e),
+        }
     }
 
     /// Import a distribution from a tar/vhdx file
@@ -1842,6 +2162,32 @@ pub async fn exec_command(
         command: &str,
         user: Option<&str>,
     ) -> CogniaResult<(String, String, i32)> {
+        self.exec_command_with_timeout(distro, command, user, WSL_TIMEOUT)
+            .await
+    }
+
+    /// [`Self::exec_command`] with a caller-chosen timeout
+    pub async fn exec_command_with_timeout(
+        &self,
+        distro: &str,
+        command: &str,
+        user: Option<&str>,
+        timeout: Duration,
+    ) -> CogniaResult<(String, String, i32)> {
+        self.exec_command_until(distro, command, user, timeout)
+            .await?
+            .ok_or_else(|| wsl_timed_out(timeout))
+    }
+
+    /// [`Self::exec_command_with_timeout`] with a timeout reported as `None`,
+    /// after wsl.exe has been killed
+    async fn exec_command_until(
+        &self,
+        distro: &str,
+        command: &str,
+        user: Option<&str>,
+        timeout: Duration,
+    ) -> CogniaResult<Option<(String, String, i32)>> {
         let mut args: Vec<&str> = vec!["-d", distro];
         if let Some(u) = user {
             args.push("--user");
@@ -1849,11 +2195,13 @@ pub async fn exec_command(
         }
         args.extend(&["--exec", "sh", "-c", command]);
 
-        let out = execute_wsl(&args, WSL_TIMEOUT).await?;
+        let Some(out) = run_wsl(&args, timeout, None).await? else {
+            return Ok(None);
+        };
         let stdout = Self::trim_output(&out.stdout);
         let stderr = Self::trim_output(&out.stderr);
         let exit_code = out.exit_code;
-        Ok((stdout, stderr, exit_code))
+        Ok(Some((stdout, stderr, exit_code)))
     }
 
     /// Convert a path between Windows and WSL formats using wslpath.
@@ -2236,6 +2584,37 @@ pub async fn read_distro_config(
         Ok(Self::parse_ini_content(&stdout))
     }
 
+    /// Read the per-distro /etc/wsl.conf file as-is, comments included.
+    /// Returns an empty string when the file does not exist.
+    pub async fn read_distro_config_content(&self, distro: &str) -> CogniaResult<String> {
+        let (stdout, _, code) = self
+            .exec_command(distro, "cat /etc/wsl.conf 2>/dev/null || true", None)
+            .await?;
+        if code != 0 {
+            return Ok(String::new());
+        }
+        Ok(stdout)
+    }
+
+    /// Replace the per-distro /etc/wsl.conf file with `content`.
+    /// Requires root access inside the distro.
+    pub async fn write_distro_config_content(
+        &self,
+        distro: &str,
+        content: &str,
+    ) -> CogniaResult<()> {
+        let escaped = content.replace('\'', "'\\''");
+        let cmd = format!("printf '%s' '{}' > /etc/wsl.conf", escaped);
+        let (_, stderr, code) = self.exec_command(distro, &cmd, Some("root")).await?;
+        if code != 0 {
+            return Err(CogniaError::Provider(format!(
+                "Failed to write wsl.conf: {}",
+                stderr.trim()
+            )));
+        }
+        Ok(())
+    }
+
     /// Write a setting to the per-distro /etc/wsl.conf file.
     /// Requires root access inside the distro.
     pub async fn write_distro_config(
@@ -2884,6 +3263,128 @@ pub struct WslPackageUpdateResult {
     pub exit_code: i32,
 }
 
+/// Outcome of one distro in a batch operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WslBatchStatus {
+    Succeeded,
+    Failed,
+    TimedOut,
+    /// Not run, e.g. stopped with auto-start off or cancelled
+    Skipped,
+}
+
+/// Settings shared by every distro in a batch exec or update.
+#[derive(Debug, Clone)]
+pub struct WslBatchOptions {
+    /// Per-distro limit on the command itself
+    pub timeout: Duration,
+    /// Start stopped distros and stop them again afterwards
+    pub auto_start: bool,
+    pub user: Option<String>,
+    /// Where output longer than [`WSL_BATCH_OUTPUT_LIMIT`] is written in full
+    pub spill_dir: PathBuf,
+}
+
+/// Per-distro result of a batch operation.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WslBatchItemResult {
+    pub distro: String,
+    pub status: WslBatchStatus,
+    pub exit_code: Option<i32>,
+    pub duration_ms: u64,
+    /// Combined stdout/stderr; the tail only when `output_truncated`
+    pub output: String,
+    pub output_truncated: bool,
+    /// Full output when it was truncated
+    pub output_file: Option<String>,
+    pub error: Option<String>,
+    /// The distro was stopped, started for this run and stopped again
+    pub auto_started: bool,
+    /// Package manager used by a batch update
+    pub package_manager: Option<String>,
+    /// Archive written by a batch export
+    pub file_path: Option<String>,
+}
+
+impl WslBatchItemResult {
+    fn new(distro: &str) -> Self {
+        Self {
+            distro: distro.to_string(),
+            status: WslBatchStatus::Failed,
+            exit_code: None,
+            duration_ms: 0,
+            output: String::new(),
+            output_truncated: false,
+            output_file: None,
+            error: None,
+            auto_started: false,
+            package_manager: None,
+            file_path: None,
+        }
+    }
+
+    /// Keep the tail of `output` inline and spill the whole text to a file
+    /// under `spill_dir` when it is too long
+    async fn set_output(&mut self, output: &str, spill_dir: &std::path::Path, kind: &str) {
+        let (tail, truncated) = truncate_output_tail(output, WSL_BATCH_OUTPUT_LIMIT);
+        self.output = tail.to_string();
+        self.output_truncated = truncated;
+        if !truncated {
+            return;
+        }
+        let safe_name: String = self
+            .distro
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let file = spill_dir.join(format!(
+            "{}-{}-{}.log",
+            safe_name,
+            kind,
+            chrono::Local::now().format("%Y%m%d-%H%M%S%.3f")
+        ));
+        let written = match tokio::fs::create_dir_all(spill_dir).await {
+            Ok(()) => tokio::fs::write(&file, output).await,
+            Err(e) => Err(e),
+        };
+        match written {
+            Ok(()) => self.output_file = Some(file.to_string_lossy().to_string()),
+            Err(e) => log::warn!("Failed to write batch output for {}: {}", self.distro, e),
+        }
+    }
+}
+
+/// Progress of a batch export, sent before (`status` unset) and after each distro.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WslBatchProgress {
+    pub distro: String,
+    pub completed: usize,
+    pub total: usize,
+    pub status: Option<WslBatchStatus>,
+}
+
+/// The last `limit` bytes of `output` (on a char boundary) and whether
+/// anything was cut.
+fn truncate_output_tail(output: &str, limit: usize) -> (&str, bool) {
+    if output.len() <= limit {
+        return (output, false);
+    }
+    let mut start = output.len() - limit;
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    (&output[start..], true)
+}
+
 impl Default for WslProvider {
     fn default() -> Self {
         Self::new()
@@ -2907,6 +3408,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::Search,
             Capability::List,
             Capability::Update,
+            Capability::Info,
+            Capability::RequiresElevation,
         ])
     }
 
@@ -3140,6 +3643,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                     install_path,
                     installed_at: String::new(),
                     is_global: true,
+                    kind: None,
                 }
             })
             .collect())
@@ -5097,4 +5601,61 @@ fn test_get_distro_update_command_coverage() {
         assert!(WslProvider::get_distro_update_command("swupd").is_some());
         assert!(WslProvider::get_distro_update_command("unknown").is_none());
     }
+
+    #[test]
+    fn test_truncate_output_tail_keeps_char_boundary() {
+        assert_eq!(truncate_output_tail("short", 10), ("short", false));
+        let (tail, truncated) = truncate_output_tail("abcdef", 3);
+        assert_eq!((tail, truncated), ("def", true));
+        // "é" is two bytes; cutting inside it moves forward
+        let (tail, truncated) = truncate_output_tail("aéb", 2);
+        assert_eq!((tail, truncated), ("b", true));
+    }
+
+    #[test]
+    fn test_package_manager_from_probe() {
+        let ubuntu = format!("ID=ubuntu\nID_LIKE=debian\n{}\napt\n", PM_PROBE_DELIM);
+        assert_eq!(
+            WslProvider::package_manager_from_probe(&ubuntu).as_deref(),
+            Some("apt")
+        );
+        let unknown = format!("ID=mystery\n{}\nzypper\n", PM_PROBE_DELIM);
+        assert_eq!(
+            WslProvider::package_manager_from_probe(&unknown).as_deref(),
+            Some("zypper")
+        );
+        let nothing = format!("ID=mystery\n{}\n", PM_PROBE_DELIM);
+        assert_eq!(WslProvider::package_manager_from_probe(&nothing), None);
+    }
+
+    #[test]
+    fn test_batch_update_command() {
+        assert_eq!(
+            WslProvider::batch_update_command("apt").as_deref(),
+            Some("export DEBIAN_FRONTEND=noninteractive; apt update; apt upgrade -y")
+        );
+        assert_eq!(
+            WslProvider::batch_update_command("dnf").as_deref(),
+            Some("dnf check-update; dnf upgrade -y")
+        );
+        assert_eq!(WslProvider::batch_update_command("eopkg"), None);
+    }
+
+    #[tokio::test]
+    async fn test_batch_output_spills_when_too_long() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut result = WslBatchItemResult::new("Ubuntu 22.04");
+        result.set_output("ok", dir.path(), "exec").await;
+        assert_eq!(result.output, "ok");
+        assert!(!result.output_truncated);
+        assert!(result.output_file.is_none());
+
+        let long = "x".repeat(WSL_BATCH_OUTPUT_LIMIT + 10);
+        result.set_output(&long, dir.path(), "exec").await;
+        assert!(result.output_truncated);
+        assert_eq!(result.output.len(), WSL_BATCH_OUTPUT_LIMIT);
+        let file = result.output_file.clone().unwrap();
+        assert!(file.contains("Ubuntu_22.04-exec-"));
+        assert_eq!(std::fs::read_to_string(file).unwrap(), long);
+    }
 }
@@ -0,0 +1,1187 @@
+//! Schema, validation and comment-preserving edits for the global
+//! `.wslconfig` and the per-distro `/etc/wsl.conf`.
+//!
+//! WSL ignores keys it does not know and values it cannot parse without any
+//! error, so a typo silently does nothing. [`validate`] reports those cases
+//! per key, and [`set_key`] changes a single key while leaving comments,
+//! ordering and unknown keys alone.
+
+use crate::error::{CogniaError, CogniaResult};
+use crate::platform::disk::format_size;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WslConfigKind {
+    /// `%USERPROFILE%\.wslconfig`, applies to the WSL 2 VM
+    Global,
+    /// `/etc/wsl.conf` inside one distribution
+    Distro,
+}
+
+impl WslConfigKind {
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Self::Global => ".wslconfig",
+            Self::Distro => "wsl.conf",
+        }
+    }
+
+    fn keys(self) -> &'static [KeySpec] {
+        match self {
+            Self::Global => GLOBAL_KEYS,
+            Self::Distro => DISTRO_KEYS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValueType {
+    Bool,
+    Integer {
+        min: i64,
+        max: Option<i64>,
+    },
+    /// Byte size with an optional unit, e.g. `8GB` or `512MB`
+    Size {
+        allow_zero: bool,
+    },
+    /// Windows path in `.wslconfig`, Linux path in `wsl.conf`
+    Path,
+    Text,
+    Choice(&'static [&'static str]),
+}
+
+/// Host resource a value should not exceed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HostLimit {
+    Memory,
+    Processors,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct KeySpec {
+    section: &'static str,
+    key: &'static str,
+    value: ValueType,
+    default: Option<&'static str>,
+    /// First WSL release that reads the key
+    since: Option<&'static str>,
+    limit: Option<HostLimit>,
+    description: &'static str,
+}
+
+const fn key(
+    section: &'static str,
+    key: &'static str,
+    value: ValueType,
+    default: Option<&'static str>,
+    description: &'static str,
+) -> KeySpec {
+    KeySpec {
+        section,
+        key,
+        value,
+        default,
+        since: None,
+        limit: None,
+        description,
+    }
+}
+
+impl KeySpec {
+    const fn since(mut self, version: &'static str) -> Self {
+        self.since = Some(version);
+        self
+    }
+
+    const fn limited_by(mut self, limit: HostLimit) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+const NETWORKING_MODES: &[&str] = &["NAT", "mirrored", "virtioproxy"];
+const MEMORY_RECLAIM_MODES: &[&str] = &["disabled", "gradual", "dropcache"];
+
+const GLOBAL_KEYS: &[KeySpec] = &[
+    key(
+        "wsl2",
+        "memory",
+        ValueType::Size { allow_zero: false },
+        Some("50% of host memory"),
+        "Memory assigned to the WSL 2 VM",
+    )
+    .limited_by(HostLimit::Memory),
+    key(
+        "wsl2",
+        "processors",
+        ValueType::Integer { min: 1, max: None },
+        Some("all logical processors"),
+        "Logical processors assigned to the WSL 2 VM",
+    )
+    .limited_by(HostLimit::Processors),
+    key(
+        "wsl2",
+        "swap",
+        ValueType::Size { allow_zero: true },
+        Some("25% of host memory"),
+        "Swap space added to the VM; 0 disables swap",
+    ),
+    key(
+        "wsl2",
+        "swapFile",
+        ValueType::Path,
+        Some("%USERPROFILE%\\AppData\\Local\\Temp\\swap.vhdx"),
+        "Windows path of the swap virtual disk",
+    ),
+    key(
+        "wsl2",
+        "kernel",
+        ValueType::Path,
+        None,
+        "Windows path of a custom Linux kernel",
+    ),
+    key(
+        "wsl2",
+        "kernelModules",
+        ValueType::Path,
+        None,
+        "Windows path of a VHD with custom kernel modules",
+    ),
+    key(
+        "wsl2",
+        "kernelCommandLine",
+        ValueType::Text,
+        None,
+        "Additional kernel command line arguments",
+    ),
+    key(
+        "wsl2",
+        "localhostForwarding",
+        ValueType::Bool,
+        Some("true"),
+        "Make ports bound in the VM reachable on the host via localhost",
+    ),
+    key(
+        "wsl2",
+        "safeMode",
+        ValueType::Bool,
+        Some("false"),
+        "Start WSL with many features disabled, for recovery",
+    ),
+    key(
+        "wsl2",
+        "pageReporting",
+        ValueType::Bool,
+        Some("true"),
+        "Let Windows reclaim memory the VM no longer uses",
+    ),
+    key(
+        "wsl2",
+        "guiApplications",
+        ValueType::Bool,
+        Some("true"),
+        "Enable WSLg support for Linux GUI applications",
+    ),
+    key(
+        "wsl2",
+        "debugConsole",
+        ValueType::Bool,
+        Some("false"),
+        "Open a console showing dmesg output when the VM starts",
+    ),
+    key(
+        "wsl2",
+        "nestedVirtualization",
+        ValueType::Bool,
+        Some("true"),
+        "Allow running other VMs inside WSL 2 (Windows 11 only)",
+    ),
+    key(
+        "wsl2",
+        "vmIdleTimeout",
+        ValueType::Integer { min: 0, max: None },
+        Some("60000"),
+        "Milliseconds the VM stays idle before shutting down",
+    ),
+    key(
+        "wsl2",
+        "dnsProxy",
+        ValueType::Bool,
+        Some("true"),
+        "Use the host's NAT as DNS server (NAT networking only)",
+    ),
+    key(
+        "wsl2",
+        "networkingMode",
+        ValueType::Choice(NETWORKING_MODES),
+        Some("NAT"),
+        "How the VM is connected to the host network",
+    )
+    .since("2.0.0"),
+    key(
+        "wsl2",
+        "firewall",
+        ValueType::Bool,
+        Some("true"),
+        "Apply Windows Firewall rules to WSL traffic",
+    )
+    .since("2.0.0"),
+    key(
+        "wsl2",
+        "dnsTunneling",
+        ValueType::Bool,
+        Some("true"),
+        "Answer DNS requests through Windows instead of over the virtual network",
+    )
+    .since("2.0.0"),
+    key(
+        "wsl2",
+        "autoProxy",
+        ValueType::Bool,
+        Some("true"),
+        "Use the Windows HTTP proxy settings inside WSL",
+    )
+    .since("2.0.0"),
+    key(
+        "wsl2",
+        "defaultVhdSize",
+        ValueType::Size { allow_zero: false },
+        Some("1TB"),
+        "Maximum size of the virtual disk of new distributions",
+    ),
+    key(
+        "experimental",
+        "autoMemoryReclaim",
+        ValueType::Choice(MEMORY_RECLAIM_MODES),
+        Some("disabled"),
+        "Release cached memory after the VM has been idle",
+    )
+    .since("2.0.0"),
+    key(
+        "experimental",
+        "sparseVhd",
+        ValueType::Bool,
+        Some("false"),
+        "Create the virtual disk of new distributions as sparse",
+    )
+    .since("2.0.0"),
+    key(
+        "experimental",
+        "bestEffortDnsParsing",
+        ValueType::Bool,
+        Some("false"),
+        "Drop unknown records from DNS responses (DNS tunneling only)",
+    )
+    .since("2.0.0"),
+    key(
+        "experimental",
+        "dnsTunnelingIpAddress",
+        ValueType::Text,
+        Some("10.255.255.254"),
+        "Nameserver address used when DNS tunneling is enabled",
+    )
+    .since("2.0.0"),
+    key(
+        "experimental",
+        "initialAutoProxyTimeout",
+        ValueType::Integer { min: 0, max: None },
+        Some("1000"),
+        "Milliseconds to wait for proxy settings when WSL starts",
+    )
+    .since("2.0.0"),
+    key(
+        "experimental",
+        "ignoredPorts",
+        ValueType::Text,
+        None,
+        "Comma-separated ports Linux may bind even if Windows uses them (mirrored only)",
+    )
+    .since("2.0.0"),
+    key(
+        "experimental",
+        "hostAddressLoopback",
+        ValueType::Bool,
+        Some("false"),
+        "Let host and VM reach each other through the host's IP (mirrored only)",
+    )
+    .since("2.0.0"),
+];
+
+const DISTRO_KEYS: &[KeySpec] = &[
+    key(
+        "automount",
+        "enabled",
+        ValueType::Bool,
+        Some("true"),
+        "Mount Windows drives under the automount root",
+    ),
+    key(
+        "automount",
+        "mountFsTab",
+        ValueType::Bool,
+        Some("true"),
+        "Process /etc/fstab when the distribution starts",
+    ),
+    key(
+        "automount",
+        "root",
+        ValueType::Path,
+        Some("/mnt/"),
+        "Directory Windows drives are mounted under",
+    ),
+    key(
+        "automount",
+        "options",
+        ValueType::Text,
+        None,
+        "Mount options for Windows drives, e.g. metadata,umask=22",
+    ),
+    key(
+        "network",
+        "generateHosts",
+        ValueType::Bool,
+        Some("true"),
+        "Generate /etc/hosts from the Windows hosts file",
+    ),
+    key(
+        "network",
+        "generateResolvConf",
+        ValueType::Bool,
+        Some("true"),
+        "Generate /etc/resolv.conf",
+    ),
+    key(
+        "network",
+        "hostname",
+        ValueType::Text,
+        Some("Windows hostname"),
+        "Hostname of the distribution",
+    ),
+    key(
+        "interop",
+        "enabled",
+        ValueType::Bool,
+        Some("true"),
+        "Allow launching Windows programs",
+    ),
+    key(
+        "interop",
+        "appendWindowsPath",
+        ValueType::Bool,
+        Some("true"),
+        "Append the Windows PATH to $PATH",
+    ),
+    key(
+        "user",
+        "default",
+        ValueType::Text,
+        None,
+        "User to log in as when the distribution starts",
+    ),
+    key(
+        "boot",
+        "systemd",
+        ValueType::Bool,
+        Some("false"),
+        "Run systemd as PID 1",
+    )
+    .since("0.67.6"),
+    key(
+        "boot",
+        "command",
+        ValueType::Text,
+        None,
+        "Command run as root when the distribution starts",
+    ),
+    key(
+        "gpu",
+        "enabled",
+        ValueType::Bool,
+        Some("true"),
+        "Give Linux applications access to the Windows GPU",
+    ),
+    key(
+        "time",
+        "useWindowsTimezone",
+        ValueType::Bool,
+        Some("true"),
+        "Use the Windows time zone",
+    ),
+];
+
+/// Key documentation for guided editors
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WslConfigKeyDoc {
+    pub section: String,
+    pub key: String,
+    /// `boolean`, `integer`, `size`, `path`, `string` or `enum`
+    pub value_type: String,
+    pub allowed_values: Vec<String>,
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+    pub default_value: Option<String>,
+    /// First WSL release that reads the key
+    pub since: Option<String>,
+    pub description: String,
+}
+
+pub fn schema_docs(kind: WslConfigKind) -> Vec<WslConfigKeyDoc> {
+    kind.keys()
+        .iter()
+        .map(|spec| {
+            let (value_type, allowed_values, min, max) = match spec.value {
+                ValueType::Bool => ("boolean", vec!["true", "false"], None, None),
+                ValueType::Integer { min, max } => ("integer", Vec::new(), Some(min), max),
+                ValueType::Size { allow_zero } => {
+                    ("size", Vec::new(), Some(i64::from(!allow_zero)), None)
+                }
+                ValueType::Path => ("path", Vec::new(), None, None),
+                ValueType::Text => ("string", Vec::new(), None, None),
+                ValueType::Choice(values) => ("enum", values.to_vec(), None, None),
+            };
+            WslConfigKeyDoc {
+                section: spec.section.to_string(),
+                key: spec.key.to_string(),
+                value_type: value_type.to_string(),
+                allowed_values: allowed_values.into_iter().map(String::from).collect(),
+                min,
+                max,
+                default_value: spec.default.map(String::from),
+                since: spec.since.map(String::from),
+                description: spec.description.to_string(),
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WslConfigSeverity {
+    /// WSL will ignore or reject the value
+    Error,
+    /// The value is accepted but probably not what was meant
+    Warning,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WslConfigDiagnostic {
+    /// Stable machine-readable code, e.g. `unknown_key` or `exceeds_host`
+    pub code: String,
+    pub severity: WslConfigSeverity,
+    pub message: String,
+    /// 1-based line in the file
+    pub line: u32,
+    pub section: Option<String>,
+    pub key: Option<String>,
+}
+
+/// Facts about the machine the file is checked against. Checks that need a
+/// missing fact are skipped.
+#[derive(Debug, Clone, Default)]
+pub struct WslConfigContext {
+    /// Installed WSL version, from `wsl --version`
+    pub wsl_version: Option<String>,
+    pub host_memory_bytes: Option<u64>,
+    pub host_processors: Option<u32>,
+}
+
+/// Check every section and key in `content` against the schema of `kind`
+pub fn validate(
+    content: &str,
+    kind: WslConfigKind,
+    context: &WslConfigContext,
+) -> Vec<WslConfigDiagnostic> {
+    let specs = kind.keys();
+    let mut diagnostics = Vec::new();
+    let mut section: Option<String> = None;
+    let mut section_known = false;
+    let mut seen: HashMap<(String, String), u32> = HashMap::new();
+
+    for (index, raw) in content.lines().enumerate() {
+        let line = index as u32 + 1;
+        let trimmed = raw.trim();
+        if trimmed.is_empty() || is_comment(trimmed) {
+            continue;
+        }
+
+        if trimmed.starts_with('[') {
+            section = section_name(trimmed).map(String::from);
+            let Some(name) = section.as_deref() else {
+                diagnostics.push(diagnostic(
+                    "syntax",
+                    WslConfigSeverity::Error,
+                    format!("Section header `{}` is missing `]`", trimmed),
+                    line,
+                    None,
+                    None,
+                ));
+                continue;
+            };
+            section_known = specs.iter().any(|s| s.section == name);
+            if !section_known {
+                let message = match specs.iter().find(|s| s.section.eq_ignore_ascii_case(name)) {
+                    Some(spec) => format!(
+                        "Unknown section [{}]; did you mean [{}]?",
+                        name, spec.section
+                    ),
+                    None => format!(
+                        "Unknown section [{}]; {} ignores it",
+                        name,
+                        kind.file_name()
+                    ),
+                };
+                diagnostics.push(diagnostic(
+                    "unknown_section",
+                    WslConfigSeverity::Warning,
+                    message,
+                    line,
+                    Some(name),
+                    None,
+                ));
+            }
+            continue;
+        }
+
+        let Some((key, value)) = trimmed.split_once('=') else {
+            diagnostics.push(diagnostic(
+                "syntax",
+                WslConfigSeverity::Error,
+                format!("Expected `key = value`, found `{}`", trimmed),
+                line,
+                section.as_deref(),
+                None,
+            ));
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        let Some(current) = section.as_deref() else {
+            diagnostics.push(diagnostic(
+                "key_outside_section",
+                WslConfigSeverity::Error,
+                format!("`{}` is not inside a section and is ignored", key),
+                line,
+                None,
+                Some(key),
+            ));
+            continue;
+        };
+        if !section_known {
+            continue;
+        }
+
+        if let Some(first) = seen.insert((current.to_string(), key.to_string()), line) {
+            diagnostics.push(diagnostic(
+                "duplicate_key",
+                WslConfigSeverity::Warning,
+                format!(
+                    "`{}` is also set on line {}; only one value applies",
+                    key, first
+                ),
+                line,
+                Some(current),
+                Some(key),
+            ));
+        }
+
+        match specs.iter().find(|s| s.section == current && s.key == key) {
+            Some(spec) => {
+                for (code, severity, message) in check_value(spec, value, kind, context) {
+                    diagnostics.push(diagnostic(
+                        code,
+                        severity,
+                        message,
+                        line,
+                        Some(current),
+                        Some(key),
+                    ));
+                }
+            }
+            None => {
+                let message = if let Some(spec) = specs
+                    .iter()
+                    .find(|s| s.section == current && s.key.eq_ignore_ascii_case(key))
+                {
+                    format!("Unknown key `{}`; did you mean `{}`?", key, spec.key)
+                } else if let Some(spec) = specs.iter().find(|s| s.key == key) {
+                    format!("`{}` belongs in [{}], not [{}]", key, spec.section, current)
+                } else {
+                    format!(
+                        "Unknown key `{}` in [{}]; {} ignores it",
+                        key,
+                        current,
+                        kind.file_name()
+                    )
+                };
+                diagnostics.push(diagnostic(
+                    "unknown_key",
+                    WslConfigSeverity::Warning,
+                    message,
+                    line,
+                    Some(current),
+                    Some(key),
+                ));
+            }
+        }
+    }
+
+    diagnostics
+}
+
+fn diagnostic(
+    code: &str,
+    severity: WslConfigSeverity,
+    message: String,
+    line: u32,
+    section: Option<&str>,
+    key: Option<&str>,
+) -> WslConfigDiagnostic {
+    WslConfigDiagnostic {
+        code: code.to_string(),
+        severity,
+        message,
+        line,
+        section: section.map(String::from),
+        key: key.map(String::from),
+    }
+}
+
+fn check_value(
+    spec: &KeySpec,
+    raw: &str,
+    kind: WslConfigKind,
+    context: &WslConfigContext,
+) -> Vec<(&'static str, WslConfigSeverity, String)> {
+    let mut findings = Vec::new();
+    let value = unquote(raw);
+    if value.is_empty() {
+        findings.push((
+            "invalid_value",
+            WslConfigSeverity::Error,
+            format!("`{}` has no value", spec.key),
+        ));
+        return findings;
+    }
+
+    match spec.value {
+        ValueType::Bool => {
+            if !value.eq_ignore_ascii_case("true") && !value.eq_ignore_ascii_case("false") {
+                findings.push((
+                    "invalid_value",
+                    WslConfigSeverity::Error,
+                    format!("`{}` must be true or false, found `{}`", spec.key, value),
+                ));
+            }
+        }
+        ValueType::Integer { min, max } => match value.parse::<i64>() {
+            Ok(n) if n < min || max.is_some_and(|max| n > max) => findings.push((
+                "out_of_range",
+                WslConfigSeverity::Error,
+                match max {
+                    Some(max) => format!("`{}` must be between {} and {}", spec.key, min, max),
+                    None => format!("`{}` must be at least {}", spec.key, min),
+                },
+            )),
+            Ok(n) => {
+                if spec.limit == Some(HostLimit::Processors) {
+                    if let Some(host) = context.host_processors {
+                        if n > i64::from(host) {
+                            findings.push((
+                                "exceeds_host",
+                                WslConfigSeverity::Warning,
+                                format!("{} processors requested but the host has {}", n, host),
+                            ));
+                        }
+                    }
+                }
+            }
+            Err(_) => findings.push((
+                "invalid_value",
+                WslConfigSeverity::Error,
+                format!("`{}` must be a whole number, found `{}`", spec.key, value),
+            )),
+        },
+        ValueType::Size { allow_zero } => match parse_size(value) {
+            Some(0) if !allow_zero => findings.push((
+                "out_of_range",
+                WslConfigSeverity::Error,
+                format!("`{}` must be larger than 0", spec.key),
+            )),
+            Some(bytes) => {
+                if value.bytes().all(|b| b.is_ascii_digit()) && bytes > 0 {
+                    findings.push((
+                        "missing_unit",
+                        WslConfigSeverity::Warning,
+                        format!(
+                            "`{}` has no unit and is read as {} bytes; add GB or MB",
+                            spec.key, bytes
+                        ),
+                    ));
+                }
+                if spec.limit == Some(HostLimit::Memory) {
+                    if let Some(host) = context.host_memory_bytes {
+                        if bytes > host {
+                            findings.push((
+                                "exceeds_host",
+                                WslConfigSeverity::Warning,
+                                format!(
+                                    "{} requested but the host has {} of memory",
+                                    format_size(bytes),
+                                    format_size(host)
+                                ),
+                            ));
+                        }
+                    }
+                }
+            }
+            None => findings.push((
+                "invalid_value",
+                WslConfigSeverity::Error,
+                format!(
+                    "`{}` must be a size such as 4GB or 512MB, found `{}`",
+                    spec.key, value
+                ),
+            )),
+        },
+        ValueType::Path => {
+            if kind == WslConfigKind::Global && value.replace("\\\\", "").contains('\\') {
+                findings.push((
+                    "unescaped_path",
+                    WslConfigSeverity::Warning,
+                    format!(
+                        "Backslashes in `{}` must be doubled (C:\\\\path) or replaced with /",
+                        spec.key
+                    ),
+                ));
+            }
+        }
+        ValueType::Text => {}
+        ValueType::Choice(values) => {
+            if !values.iter().any(|v| v.eq_ignore_ascii_case(value)) {
+                findings.push((
+                    "invalid_value",
+                    WslConfigSeverity::Error,
+                    format!(
+                        "`{}` must be one of {}, found `{}`",
+                        spec.key,
+                        values.join(", "),
+                        value
+                    ),
+                ));
+            }
+        }
+    }
+
+    if let (Some(since), Some(installed)) = (spec.since, context.wsl_version.as_deref()) {
+        if !version_at_least(installed, since) {
+            findings.push((
+                "unsupported_version",
+                WslConfigSeverity::Warning,
+                format!(
+                    "`{}` needs WSL {} or newer; {} is installed",
+                    spec.key, since, installed
+                ),
+            ));
+        }
+    }
+
+    findings
+}
+
+/// Set `key` in `section` to `value`, or remove it when `value` is `None`.
+/// The last occurrence of the key is rewritten in place and earlier
+/// duplicates are dropped; everything else, comments included, is kept.
+/// Missing keys go after the last entry of the section, missing sections at
+/// the end of the file. Sections and keys match case-insensitively, like WSL
+/// reads them; names or values that would break the line structure are
+/// refused.
+pub fn set_key(
+    content: &str,
+    section: &str,
+    key: &str,
+    value: Option<&str>,
+) -> CogniaResult<String> {
+    check_name("section", section)?;
+    check_name("key", key)?;
+    if value.is_some_and(|v| v.contains(['\r', '\n'])) {
+        return Err(CogniaError::Config(format!(
+            "The value for `{}` must be a single line",
+            key
+        )));
+    }
+
+    let newline = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut lines: Vec<String> = content.lines().map(String::from).collect();
+
+    let mut current: Option<&str> = None;
+    let mut key_lines = Vec::new();
+    let mut insert_at = None;
+    for (index, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        if let Some(name) = section_name(trimmed) {
+            current = Some(name);
+            if name.eq_ignore_ascii_case(section) {
+                insert_at = Some(index + 1);
+            }
+            continue;
+        }
+        if !current.is_some_and(|c| c.eq_ignore_ascii_case(section))
+            || trimmed.is_empty()
+            || is_comment(trimmed)
+        {
+            continue;
+        }
+        insert_at = Some(index + 1);
+        if trimmed
+            .split_once('=')
+            .is_some_and(|(k, _)| k.trim().eq_ignore_ascii_case(key))
+        {
+            key_lines.push(index);
+        }
+    }
+
+    match (value, key_lines.split_last()) {
+        (Some(value), Some((&last, earlier))) => {
+            lines[last] = replace_entry(&lines[last], key, value);
+            for &index in earlier.iter().rev() {
+                lines.remove(index);
+            }
+        }
+        (Some(value), None) => {
+            let entry = format!("{}={}", key, value);
+            match insert_at {
+                Some(index) => lines.insert(index, entry),
+                None => {
+                    if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+                        lines.push(String::new());
+                    }
+                    lines.push(format!("[{}]", section));
+                    lines.push(entry);
+                }
+            }
+        }
+        (None, _) => {
+            for &index in key_lines.iter().rev() {
+                lines.remove(index);
+            }
+        }
+    }
+
+    let mut output = lines.join(newline);
+    if !output.is_empty() {
+        output.push_str(newline);
+    }
+    Ok(output)
+}
+
+/// Section and key names end at `]` and `=`, and a line break would start a
+/// new entry
+fn check_name(what: &str, name: &str) -> CogniaResult<()> {
+    if name.trim().is_empty() {
+        return Err(CogniaError::Config(format!("The {} name is empty", what)));
+    }
+    if name.contains(['\r', '\n', '[', ']', '=']) {
+        return Err(CogniaError::Config(format!(
+            "The {} name `{}` must not contain line breaks, `[`, `]` or `=`",
+            what,
+            name.escape_debug()
+        )));
+    }
+    Ok(())
+}
+
+/// Keep the indentation and the spacing around `=`, swap the value and spell
+/// the key as requested
+fn replace_entry(line: &str, key: &str, value: &str) -> String {
+    match line.find('=') {
+        Some(eq) => {
+            let before = &line[..eq];
+            let indent = &before[..before.len() - before.trim_start().len()];
+            let padding = &before[before.trim_end().len()..];
+            let after = &line[eq + 1..];
+            let spacing = &after[..after.len() - after.trim_start().len()];
+            format!("{}{}{}={}{}", indent, key, padding, spacing, value)
+        }
+        None => line.to_string(),
+    }
+}
+
+fn is_comment(trimmed: &str) -> bool {
+    trimmed.starts_with('#') || trimmed.starts_with(';')
+}
+
+fn section_name(trimmed: &str) -> Option<&str> {
+    trimmed
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .map(str::trim)
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+/// Parse sizes the way WSL writes them: a whole number followed by an
+/// optional B, KB, MB, GB or TB (the B may be left out)
+fn parse_size(value: &str) -> Option<u64> {
+    let digits = value.bytes().take_while(|b| b.is_ascii_digit()).count();
+    if digits == 0 {
+        return None;
+    }
+    let number: u64 = value[..digits].parse().ok()?;
+    let multiplier: u64 = match value[digits..].trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" => 1 << 10,
+        "M" | "MB" => 1 << 20,
+        "G" | "GB" => 1 << 30,
+        "T" | "TB" => 1 << 40,
+        _ => return None,
+    };
+    number.checked_mul(multiplier)
+}
+
+/// Compare dotted numeric versions; parts that are not numbers count as 0
+fn version_at_least(installed: &str, required: &str) -> bool {
+    let parse = |v: &str| -> Vec<u64> {
+        v.trim()
+            .split('.')
+            .map(|part| {
+                part.chars()
+                    .take_while(char::is_ascii_digit)
+                    .collect::<String>()
+                    .parse()
+                    .unwrap_or(0)
+            })
+            .collect()
+    };
+    let (installed, required) = (parse(installed), parse(required));
+    let len = installed.len().max(required.len());
+    for i in 0..len {
+        let a = installed.get(i).copied().unwrap_or(0);
+        let b = required.get(i).copied().unwrap_or(0);
+        if a != b {
+            return a > b;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(diagnostics: &[WslConfigDiagnostic]) -> Vec<(&str, u32)> {
+        diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.line))
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_accepts_known_keys() {
+        let content = "# limits\n[wsl2]\nmemory = 8GB\nprocessors=4\nnetworkingMode=mirrored\n\n[experimental]\nautoMemoryReclaim=gradual\n";
+        let context = WslConfigContext {
+            wsl_version: Some("2.3.26.0".into()),
+            host_memory_bytes: Some(32 << 30),
+            host_processors: Some(16),
+        };
+        assert!(validate(content, WslConfigKind::Global, &context).is_empty());
+    }
+
+    #[test]
+    fn test_validate_reports_bad_keys_and_values() {
+        let content = "stray=1\n[wsl2]\nmemory=8 gigs\nprocesors=2\nsparseVhd=true\nlocalhostForwarding=yes\nswap=0\nswap=4GB\n[wsl3]\nfoo=bar\n[wsl2\n";
+        let diagnostics = validate(content, WslConfigKind::Global, &WslConfigContext::default());
+        assert_eq!(
+            codes(&diagnostics),
+            vec![
+                ("key_outside_section", 1),
+                ("invalid_value", 3),
+                ("unknown_key", 4),
+                ("unknown_key", 5),
+                ("invalid_value", 6),
+                ("duplicate_key", 8),
+                ("unknown_section", 9),
+                ("syntax", 11),
+            ]
+        );
+        assert!(diagnostics[3].message.contains("belongs in [experimental]"));
+        assert_eq!(diagnostics[1].key.as_deref(), Some("memory"));
+        assert_eq!(diagnostics[1].section.as_deref(), Some("wsl2"));
+    }
+
+    #[test]
+    fn test_validate_suggests_key_case() {
+        let diagnostics = validate(
+            "[boot]\nSystemd=true\n",
+            WslConfigKind::Distro,
+            &WslConfigContext::default(),
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.contains("did you mean `systemd`"));
+    }
+
+    #[test]
+    fn test_validate_checks_host_resources_and_version() {
+        let context = WslConfigContext {
+            wsl_version: Some("1.2.5.0".into()),
+            host_memory_bytes: Some(16 << 30),
+            host_processors: Some(8),
+        };
+        let diagnostics = validate(
+            "[wsl2]\nmemory=64GB\nprocessors=12\nfirewall=true\n",
+            WslConfigKind::Global,
+            &context,
+        );
+        assert_eq!(
+            codes(&diagnostics),
+            vec![
+                ("exceeds_host", 2),
+                ("exceeds_host", 3),
+                ("unsupported_version", 4)
+            ]
+        );
+        assert!(diagnostics[0].message.contains("64.00 GB"));
+    }
+
+    #[test]
+    fn test_validate_ranges_units_and_paths() {
+        let diagnostics = validate(
+            "[wsl2]\nprocessors=0\nmemory=0GB\nswap=4096\nkernel=C:\\kernels\\bzImage\nswapFile=C:\\\\temp\\\\swap.vhdx\nnetworkingMode=nat\n",
+            WslConfigKind::Global,
+            &WslConfigContext::default(),
+        );
+        assert_eq!(
+            codes(&diagnostics),
+            vec![
+                ("out_of_range", 2),
+                ("out_of_range", 3),
+                ("missing_unit", 4),
+                ("unescaped_path", 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_set_key_preserves_comments_and_unknown_keys() {
+        let content = "# tuned for builds\n[wsl2]\nmemory = 4GB  \ncustomKey=1\n\n# network\n[experimental]\nsparseVhd=true\n";
+        let updated = set_key(content, "wsl2", "memory", Some("8GB")).unwrap();
+        assert_eq!(
+            updated,
+            "# tuned for builds\n[wsl2]\nmemory = 8GB\ncustomKey=1\n\n# network\n[experimental]\nsparseVhd=true\n"
+        );
+
+        let updated = set_key(content, "wsl2", "processors", Some("4")).unwrap();
+        assert_eq!(
+            updated,
+            "# tuned for builds\n[wsl2]\nmemory = 4GB  \ncustomKey=1\nprocessors=4\n\n# network\n[experimental]\nsparseVhd=true\n"
+        );
+
+        let updated = set_key(content, "experimental", "sparseVhd", None).unwrap();
+        assert_eq!(
+            updated,
+            "# tuned for builds\n[wsl2]\nmemory = 4GB  \ncustomKey=1\n\n# network\n[experimental]\n"
+        );
+    }
+
+    #[test]
+    fn test_set_key_adds_sections_and_collapses_duplicates() {
+        assert_eq!(
+            set_key("", "boot", "systemd", Some("true")).unwrap(),
+            "[boot]\nsystemd=true\n"
+        );
+        assert_eq!(
+            set_key("[user]\ndefault=dev", "boot", "systemd", Some("true")).unwrap(),
+            "[user]\ndefault=dev\n\n[boot]\nsystemd=true\n"
+        );
+        assert_eq!(
+            set_key(
+                "[wsl2]\r\nswap=0\r\nswap=2GB\r\n",
+                "wsl2",
+                "swap",
+                Some("4GB")
+            )
+            .unwrap(),
+            "[wsl2]\r\nswap=4GB\r\n"
+        );
+        assert_eq!(
+            set_key("[wsl2]\n", "wsl2", "swap", None).unwrap(),
+            "[wsl2]\n"
+        );
+    }
+
+    #[test]
+    fn test_set_key_matches_case_insensitively() {
+        assert_eq!(
+            set_key("[WSL2]\n  Memory = 4GB\n", "wsl2", "memory", Some("8GB")).unwrap(),
+            "[WSL2]\n  memory = 8GB\n"
+        );
+        assert_eq!(
+            set_key("[wsl2]\nSWAP=0\nswap=2GB\n", "wsl2", "swap", None).unwrap(),
+            "[wsl2]\n"
+        );
+    }
+
+    #[test]
+    fn test_set_key_rejects_injection() {
+        let content = "[wsl2]\nmemory=4GB\n";
+        for (section, key, value) in [
+            ("wsl2", "memory", "8GB\n[boot]\ncommand=evil"),
+            ("wsl2", "memory", "8GB\rswap=0"),
+            ("wsl2]\n[boot", "memory", "8GB"),
+            ("wsl2", "command=evil\nmemory", "8GB"),
+            ("wsl2", "mem[ory]", "8GB"),
+            ("wsl2", "swap=0", "8GB"),
+            ("", "memory", "8GB"),
+        ] {
+            assert!(
+                set_key(content, section, key, Some(value)).is_err(),
+                "{:?} {:?} {:?}",
+                section,
+                key,
+                value
+            );
+        }
+        assert!(set_key(content, "wsl2", "memory\n", None).is_err());
+    }
+
+    #[test]
+    fn test_parse_size_and_versions() {
+        assert_eq!(parse_size("8GB"), Some(8 << 30));
+        assert_eq!(parse_size("512mb"), Some(512 << 20));
+        assert_eq!(parse_size("2G"), Some(2 << 30));
+        assert_eq!(parse_size("1024"), Some(1024));
+        assert_eq!(parse_size("GB"), None);
+        assert_eq!(parse_size("1.5GB"), None);
+
+        assert!(version_at_least("2.0.14.0", "2.0.0"));
+        assert!(version_at_least("0.67.6", "0.67.6"));
+        assert!(!version_at_least("0.67.5.0", "0.67.6"));
+        assert!(!version_at_least("1.2.5.0", "2.0.0"));
+    }
+
+    #[test]
+    fn test_schema_docs() {
+        let docs = schema_docs(WslConfigKind::Global);
+        let mode = docs.iter().find(|d| d.key == "networkingMode").unwrap();
+        assert_eq!(mode.value_type, "enum");
+        assert_eq!(mode.allowed_values, vec!["NAT", "mirrored", "virtioproxy"]);
+        assert_eq!(mode.since.as_deref(), Some("2.0.0"));
+
+        let docs = schema_docs(WslConfigKind::Distro);
+        assert!(docs
+            .iter()
+            .any(|d| d.section == "boot" && d.key == "systemd"));
+    }
+}
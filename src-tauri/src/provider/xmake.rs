@@ -112,7 +112,7 @@ async fn run_xrepo_lenient(args: &[&str]) -> CogniaResult<String> {
         Ok(combined)
     }
 
-    fn get_xmake_packages_dir() -> Option<PathBuf> {
+    pub(crate) fn get_xmake_packages_dir() -> Option<PathBuf> {
         // Check XMAKE_PKG_INSTALLDIR or default ~/.xmake/packages
         std::env::var("XMAKE_PKG_INSTALLDIR")
             .ok()
@@ -525,7 +525,7 @@ fn parse_scan_output(output: &str) -> Vec<(String, String)> {
     }
 
     /// Strip xmake color markers like `${red}`, `${clear}`, etc.
-    fn strip_xmake_colors(s: &str) -> String {
+    pub(crate) fn strip_xmake_colors(s: &str) -> String {
         let mut result = String::with_capacity(s.len());
         let mut chars = s.chars().peekable();
         while let Some(ch) = chars.next() {
@@ -614,6 +614,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::Update,
             Capability::Upgrade,
             Capability::UpdateIndex,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
     fn supported_platforms(&self) -> Vec<Platform> {
@@ -876,6 +878,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                     install_path: install_path.clone(),
                     installed_at: String::new(),
                     is_global: true,
+                    kind: None,
                 })
             })
             .collect())
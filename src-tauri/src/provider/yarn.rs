@@ -183,7 +183,7 @@ async fn get_dependencies_via_api(
         let registry_url = self.registry_url.as_deref().unwrap_or(DEFAULT_NPM_REGISTRY);
         let url = format!("{}/{}", registry_url, pkg_path);
 
-        let client = crate::platform::proxy::get_shared_client();
+        let client = crate::platform::proxy::get_client_for_provider("yarn");
 
         let resp = client
             .get(&url)
@@ -251,6 +251,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::Update,
             Capability::Upgrade,
             Capability::LockVersion,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
 
@@ -464,6 +466,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                 provider: self.id().into(),
                 installed_at: String::new(),
                 is_global: true,
+                kind: None,
             })
             .collect();
 
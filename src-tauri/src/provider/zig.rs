@@ -19,16 +19,28 @@
 pub struct ZigProvider {
     zig_dir: Option<PathBuf>,
     client: Client,
+    /// Download cache that keeps install archives for installed versions
+    cache_dir: Option<PathBuf>,
+    index_url: String,
 }
 
+const ZIG_INDEX_URL: &str = "https://ziglang.org/download/index.json";
+
 impl ZigProvider {
     pub fn new() -> Self {
         Self {
             zig_dir: Self::detect_zig_dir(),
-            client: crate::platform::proxy::get_shared_client(),
+            client: crate::platform::proxy::get_client_for_provider("zig"),
+            cache_dir: None,
+            index_url: ZIG_INDEX_URL.to_string(),
         }
     }
 
+    pub fn with_cache_dir(mut self, cache_dir: Option<PathBuf>) -> Self {
+        self.cache_dir = cache_dir;
+        self
+    }
+
     fn detect_zig_dir() -> Option<PathBuf> {
         if cfg!(windows) {
             std::env::var("LOCALAPPDATA")
@@ -262,17 +274,29 @@ fn get_platform_key() -> Option<&'static str> {
         })
     }
 
-    /// Fetch the official Zig download index and return available versions.
-    async fn fetch_available_versions(&self) -> CogniaResult<Vec<(String, Option<String>)>> {
-        let url = "https://ziglang.org/download/index.json";
-
-        let response = self
-            .client
-            .get(url)
+    /// Fetch the official Zig download index, sending the cursor's validators
+    /// so an unchanged index answers `304 Not Modified` (returned as `None`).
+    async fn fetch_index(
+        &self,
+        cursor: Option<&VersionSyncCursor>,
+    ) -> CogniaResult<Option<(serde_json::Value, Option<String>, Option<String>)>> {
+        let mut request = self.client.get(&self.index_url);
+        if let Some(cursor) = cursor {
+            if let Some(etag) = &cursor.etag {
+                request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &cursor.last_modified {
+                request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+        let response = request
             .send()
             .await
             .map_err(|e| CogniaError::Network(e.to_string()))?;
 
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(None);
+        }
         if !response.status().is_success() {
             return Err(CogniaError::Network(format!(
                 "Failed to fetch Zig versions: {}",
@@ -280,11 +304,37 @@ async fn fetch_available_versions(&self) -> CogniaResult<Vec<(String, Option<Str
             )));
         }
 
+        let header = |name: reqwest::header::HeaderName| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(String::from)
+        };
+        let etag = header(reqwest::header::ETAG);
+        let last_modified = header(reqwest::header::LAST_MODIFIED);
+
         let index: serde_json::Value = response
             .json()
             .await
             .map_err(|e| CogniaError::Parse(e.to_string()))?;
 
+        Ok(Some((index, etag, last_modified)))
+    }
+
+    /// Fetch the official Zig download index and return available versions.
+    async fn fetch_available_versions(&self) -> CogniaResult<Vec<(String, Option<String>)>> {
+        let (index, _, _) = self
+            .fetch_index(None)
+            .await?
+            .ok_or_else(|| CogniaError::Network("Zig download index was not returned".into()))?;
+        Self::parse_index_versions(&index)
+    }
+
+    /// Versions in the download index that have a build for this platform.
+    fn parse_index_versions(
+        index: &serde_json::Value,
+    ) -> CogniaResult<Vec<(String, Option<String>)>> {
         let platform_key = Self::get_platform_key();
 
         let obj = index
@@ -331,12 +381,23 @@ async fn fetch_available_versions(&self) -> CogniaResult<Vec<(String, Option<Str
         Ok(versions)
     }
 
+    fn to_version_infos(versions: Vec<(String, Option<String>)>) -> Vec<VersionInfo> {
+        versions
+            .into_iter()
+            .map(|(version, date)| VersionInfo {
+                version,
+                release_date: date,
+                deprecated: false,
+                yanked: false,
+            })
+            .collect()
+    }
+
     /// Get the download URL and optional SHA256 hash for a specific version and the current platform.
     async fn get_download_url(&self, version: &str) -> CogniaResult<(String, Option<String>)> {
-        let url = "https://ziglang.org/download/index.json";
         let response = self
             .client
-            .get(url)
+            .get(&self.index_url)
             .send()
             .await
             .map_err(|e| CogniaError::Network(e.to_string()))?;
@@ -525,6 +586,8 @@ fn capabilities(&self) -> HashSet<Capability> {
             Capability::MultiVersion,
             Capability::Update,
             Capability::ProjectLocal,
+            Capability::Info,
+            Capability::Versions,
         ])
     }
 
@@ -620,16 +683,23 @@ async fn get_installed_version(&self, name: &str) -> CogniaResult<Option<String>
 
     async fn get_versions(&self, _name: &str) -> CogniaResult<Vec<VersionInfo>> {
         let versions = self.fetch_available_versions().await?;
+        Ok(Self::to_version_infos(versions))
+    }
 
-        Ok(versions
-            .into_iter()
-            .map(|(version, date)| VersionInfo {
-                version,
-                release_date: date,
-                deprecated: false,
-                yanked: false,
-            })
-            .collect())
+    async fn fetch_versions_incremental(
+        &self,
+        _name: &str,
+        cursor: &VersionSyncCursor,
+    ) -> CogniaResult<VersionFetch> {
+        // The index is one small document, so a conditional GET is all we need
+        let Some((index, etag, last_modified)) = self.fetch_index(Some(cursor)).await? else {
+            return Ok(VersionFetch::Unchanged);
+        };
+        Ok(VersionFetch::Full {
+            versions: Self::to_version_infos(Self::parse_index_versions(&index)?),
+            etag,
+            last_modified,
+        })
     }
 
     async fn get_dependencies(
@@ -684,27 +754,40 @@ async fn install(&self, req: InstallRequest) -> CogniaResult<InstallReceipt> {
 
         // Get download URL and expected checksum
         let (download_url, expected_shasum) = self.get_download_url(&actual_version).await?;
+        // The download cache is keyed by SHA256, so only verified archives use it
+        let cache = self.cache_dir.as_deref().zip(expected_shasum.as_deref());
+        let cached = match cache {
+            Some((cache_dir, checksum)) => {
+                crate::cache::download::cached_env_artifact(cache_dir, checksum).await
+            }
+            None => None,
+        };
 
-        // Download the archive
-        let response = self
-            .client
-            .get(&download_url)
-            .timeout(Duration::from_secs(600))
-            .send()
-            .await
-            .map_err(|e| CogniaError::Network(e.to_string()))?;
+        // Download the archive unless an earlier install cached it
+        let bytes = if let Some(cached) = cached {
+            tokio::fs::read(&cached).await?
+        } else {
+            let response = self
+                .client
+                .get(&download_url)
+                .timeout(Duration::from_secs(600))
+                .send()
+                .await
+                .map_err(|e| CogniaError::Network(e.to_string()))?;
 
-        if !response.status().is_success() {
-            return Err(CogniaError::Network(format!(
-                "Download failed: {}",
-                response.status()
-            )));
-        }
+            if !response.status().is_success() {
+                return Err(CogniaError::Network(format!(
+                    "Download failed: {}",
+                    response.status()
+                )));
+            }
 
-        let bytes = response
-            .bytes()
-            .await
-            .map_err(|e| CogniaError::Network(e.to_string()))?;
+            response
+                .bytes()
+                .await
+                .map_err(|e| CogniaError::Network(e.to_string()))?
+                .to_vec()
+        };
 
         // Verify SHA256 checksum if provided by the download index
         if let Some(ref expected) = expected_shasum {
@@ -746,10 +829,19 @@ async fn install(&self, req: InstallRequest) -> CogniaResult<InstallReceipt> {
         tokio::fs::write(&archive_path, &bytes)
             .await
             .map_err(|e| CogniaError::Io(std::io::Error::other(e.to_string())))?;
+        if let Some((cache_dir, checksum)) = cache {
+            crate::cache::download::store_env_artifact(
+                cache_dir,
+                &archive_path,
+                checksum,
+                &download_url,
+            )
+            .await;
+        }
 
         // Extract using shell commands (same approach as core::installer)
         let extract_result =
-            crate::core::installer::extract_archive(&archive_path, &temp_dir).await;
+            crate::core::archive::extract_archive(&archive_path, &temp_dir).await;
         // Cleanup archive file regardless of result
         let _ = tokio::fs::remove_file(&archive_path).await;
         extract_result.map_err(|e| CogniaError::Provider(format!("Extraction failed: {}", e)))?;
@@ -771,6 +863,16 @@ async fn install(&self, req: InstallRequest) -> CogniaResult<InstallReceipt> {
         // Cleanup temp
         let _ = tokio::fs::remove_dir_all(&temp_dir).await;
 
+        if let Some((cache_dir, checksum)) = cache {
+            crate::cache::download::reference_env_artifact(
+                cache_dir,
+                checksum,
+                "zig",
+                &actual_version,
+            )
+            .await;
+        }
+
         Ok(InstallReceipt {
             name: "zig".to_string(),
             version: actual_version,
@@ -836,6 +938,7 @@ async fn list_installed(&self, filter: InstalledFilter) -> CogniaResult<Vec<Inst
                     install_path: path,
                     installed_at,
                     is_global: true,
+                    kind: None,
                 }
             })
             .collect();
@@ -1080,6 +1183,8 @@ fn provider_with_dir(dir: &Path) -> ZigProvider {
         ZigProvider {
             zig_dir: Some(dir.to_path_buf()),
             client: reqwest::Client::new(),
+            cache_dir: None,
+            index_url: ZIG_INDEX_URL.to_string(),
         }
     }
 
@@ -1135,7 +1240,7 @@ fn test_provider_metadata() {
         assert!(provider.capabilities().contains(&Capability::MultiVersion));
         assert!(provider.capabilities().contains(&Capability::Update));
         assert!(provider.capabilities().contains(&Capability::ProjectLocal));
-        assert_eq!(provider.capabilities().len(), 8);
+        assert_eq!(provider.capabilities().len(), 10);
         assert_eq!(provider.priority(), 80);
     }
 
@@ -1452,6 +1557,8 @@ fn test_zig_dir_none_returns_error() {
         let provider = ZigProvider {
             zig_dir: None,
             client: reqwest::Client::new(),
+            cache_dir: None,
+            index_url: ZIG_INDEX_URL.to_string(),
         };
         assert!(provider.zig_dir().is_err());
     }
@@ -1749,6 +1856,8 @@ async fn test_get_installed_version_no_zig_dir() {
         let provider = ZigProvider {
             zig_dir: None,
             client: reqwest::Client::new(),
+            cache_dir: None,
+            index_url: ZIG_INDEX_URL.to_string(),
         };
         let result = provider.get_installed_version("zig@0.13.0").await.unwrap();
         assert_eq!(result, None);
@@ -2173,4 +2282,109 @@ async fn test_set_global_version_not_installed() {
         let err = result.unwrap_err().to_string();
         assert!(err.contains("not installed"), "Error: {}", err);
     }
+
+    // ════════════════════════════════════════════════════════════════
+    //  Install through the download cache
+    // ════════════════════════════════════════════════════════════════
+
+    /// Serve `index.json` and `zig.zip` over plain HTTP, counting archive hits
+    async fn serve_zig_release(
+        archive: Vec<u8>,
+        checksum: &str,
+    ) -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let index = serde_json::json!({
+            "0.13.0": {
+                "date": "2024-06-07",
+                ZigProvider::get_platform_key().unwrap(): {
+                    "tarball": format!("{}/zig.zip", base),
+                    "shasum": checksum,
+                    "size": archive.len().to_string(),
+                }
+            }
+        })
+        .to_string()
+        .into_bytes();
+        let archive_hits = std::sync::Arc::new(AtomicUsize::new(0));
+        let hits = archive_hits.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = vec![0u8; 4096];
+                let n = stream.read(&mut request).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&request[..n]);
+                let body = if request.starts_with("GET /zig.zip") {
+                    hits.fetch_add(1, Ordering::SeqCst);
+                    &archive
+                } else {
+                    &index
+                };
+                let head = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                let _ = stream.write_all(head.as_bytes()).await;
+                let _ = stream.write_all(body).await;
+            }
+        });
+        (format!("{}/index.json", base), archive_hits)
+    }
+
+    #[tokio::test]
+    async fn test_install_references_and_reuses_cached_archive() {
+        use std::io::Write;
+        use std::sync::atomic::Ordering;
+
+        if ZigProvider::get_platform_key().is_none() {
+            return;
+        }
+        let tmp = tempfile::tempdir().unwrap();
+        let mut archive = Vec::new();
+        {
+            let mut writer = zip::ZipWriter::new(std::io::Cursor::new(&mut archive));
+            let options: zip::write::FileOptions<'_, ()> = zip::write::FileOptions::default();
+            writer.start_file("zig-test-0.13.0/zig", options).unwrap();
+            writer.write_all(b"zig").unwrap();
+            writer.finish().unwrap();
+        }
+        let checksum = hex::encode(Sha256::digest(&archive));
+        let (index_url, archive_hits) = serve_zig_release(archive, &checksum).await;
+
+        let cache_dir = tmp.path().join("cache");
+        let provider = ZigProvider {
+            zig_dir: Some(tmp.path().join("zig")),
+            client: reqwest::Client::builder().no_proxy().build().unwrap(),
+            cache_dir: Some(cache_dir.clone()),
+            index_url,
+        };
+        let request = || InstallRequest {
+            name: "zig".into(),
+            version: Some("0.13.0".into()),
+            global: false,
+            force: true,
+        };
+
+        let receipt = provider.install(request()).await.unwrap();
+        assert!(receipt.install_path.join("zig").exists());
+        let references = crate::cache::DownloadCache::open(&cache_dir)
+            .await
+            .unwrap()
+            .references_by_checksum()
+            .await
+            .unwrap();
+        let labels: Vec<_> = references[&checksum]
+            .iter()
+            .map(|r| format!("{}@{}", r.env_type, r.version))
+            .collect();
+        assert_eq!(labels, vec!["zig@0.13.0"]);
+
+        // Reinstalling consumes the cached archive instead of downloading it
+        provider.install(request()).await.unwrap();
+        assert_eq!(archive_hits.load(Ordering::SeqCst), 1);
+    }
 }
@@ -1,7 +1,7 @@
 use super::{Version, VersionConstraint};
 use crate::error::{CogniaError, CogniaResult};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Package {
@@ -58,73 +58,30 @@ pub fn lock_version(&mut self, name: impl Into<String>, version: Version) {
         self.locked.insert(name.into(), version);
     }
 
+    /// Resolve with full backtracking. A failure is reported from the same
+    /// incompatibility graph [`Resolver::resolve_explained`] returns, so
+    /// both always agree.
     pub fn resolve(&self, root_deps: &[Dependency]) -> CogniaResult<Resolution> {
-        let mut solution: HashMap<String, Version> = HashMap::new();
-        let mut pending: Vec<Dependency> = root_deps.to_vec();
-        let mut visited: HashSet<String> = HashSet::new();
-
-        while let Some(dep) = pending.pop() {
-            if visited.contains(&dep.name) {
-                if let Some(resolved) = solution.get(&dep.name) {
-                    if !dep.constraint.matches(resolved) {
-                        return Err(CogniaError::Conflict(format!(
-                            "Version conflict for '{}': {} required but {} already selected",
-                            dep.name, dep.constraint, resolved
-                        )));
-                    }
-                }
-                continue;
-            }
-
-            let version = self.select_version(&dep.name, &dep.constraint, &solution)?;
-            solution.insert(dep.name.clone(), version.clone());
-            visited.insert(dep.name.clone());
-
-            if let Some(packages) = self.available.get(&dep.name) {
-                if let Some(pkg) = packages.iter().find(|p| p.version == version) {
-                    for sub_dep in &pkg.dependencies {
-                        if !visited.contains(&sub_dep.name) {
-                            pending.push(sub_dep.clone());
-                        } else if let Some(resolved) = solution.get(&sub_dep.name) {
-                            if !sub_dep.constraint.matches(resolved) {
-                                return Err(CogniaError::Conflict(format!(
-                                    "Version conflict for '{}': {} required but {} already selected",
-                                    sub_dep.name, sub_dep.constraint, resolved
-                                )));
-                            }
-                        }
-                    }
-                }
-            }
+        let explained = self.resolve_explained(root_deps, false)?;
+        if let Some(resolution) = explained.resolution {
+            return Ok(resolution);
         }
+        let Some(explanation) = explained.explanation else {
+            return Err(CogniaError::Resolution(
+                "Dependency resolution failed".into(),
+            ));
+        };
 
-        Ok(Resolution { packages: solution })
-    }
-
-    fn select_version(
-        &self,
-        name: &str,
-        constraint: &VersionConstraint,
-        _current: &HashMap<String, Version>,
-    ) -> CogniaResult<Version> {
-        if let Some(locked) = self.locked.get(name) {
-            if constraint.matches(locked) {
-                return Ok(locked.clone());
+        let root = &explanation.incompatibilities[explanation.root_incompatibility];
+        Err(match root.cause {
+            IncompatibilityCause::PackageNotFound => {
+                CogniaError::PackageNotFound(root.package.clone())
             }
-        }
-
-        let packages = self
-            .available
-            .get(name)
-            .ok_or_else(|| CogniaError::PackageNotFound(name.into()))?;
-
-        let versions: Vec<Version> = packages.iter().map(|p| p.version.clone()).collect();
-
-        constraint.select_best(&versions).cloned().ok_or_else(|| {
-            CogniaError::VersionNotFound(format!(
-                "No version of '{}' satisfies constraint {}",
-                name, constraint
-            ))
+            IncompatibilityCause::NoVersions => CogniaError::VersionNotFound(root.message.clone()),
+            _ => CogniaError::Conflict(format!(
+                "Version conflict for '{}': {}",
+                explanation.conflicting_package, explanation.summary
+            )),
         })
     }
 }
@@ -150,6 +107,586 @@ pub fn iter(&self) -> impl Iterator<Item = (&String, &Version)> {
     }
 }
 
+/// Upper bound on version decisions made by the resolver before the search
+/// is abandoned.
+const MAX_RESOLVE_STEPS: usize = 10_000;
+
+/// Why an incompatibility holds. `Conflict` and `Derived` reference other
+/// incompatibilities by id, forming the derivation graph.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum IncompatibilityCause {
+    /// Constraint requested directly by the caller.
+    Root,
+    /// `package@version` declares a dependency on the incompatibility's package.
+    Dependency { package: String, version: String },
+    /// The package is not offered by any provider.
+    PackageNotFound,
+    /// No available version satisfies the single constraint.
+    NoVersions,
+    /// The listed constraints have no version in common.
+    Conflict { causes: Vec<usize> },
+    /// Every candidate version of the package led to one of the listed failures.
+    Derived { causes: Vec<usize> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Incompatibility {
+    pub id: usize,
+    pub package: String,
+    pub constraint: String,
+    pub cause: IncompatibilityCause,
+    pub message: String,
+}
+
+/// Where one of the clashing constraints came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConstraintOrigin {
+    pub package: String,
+    pub constraint: String,
+    /// `None` for constraints requested by the caller.
+    pub required_by: Option<String>,
+    pub required_by_version: Option<String>,
+    pub incompatibility: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuggestionAction {
+    /// Pin a requiring package to an older version.
+    Pin,
+    /// Upgrade a requiring package.
+    Upgrade,
+    /// Loosen a constraint requested by the caller.
+    Relax,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolutionSuggestion {
+    pub action: SuggestionAction,
+    pub package: String,
+    pub constraint: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionKind {
+    Decide,
+    Conflict,
+    Backtrack,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecisionLogEntry {
+    pub step: usize,
+    pub kind: DecisionKind,
+    pub package: String,
+    pub version: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolutionFailureExplanation {
+    /// Package whose constraints could not be satisfied first.
+    pub conflicting_package: String,
+    pub summary: String,
+    /// Human-readable derivation, causes before conclusions.
+    pub derivation: Vec<String>,
+    pub incompatibilities: Vec<Incompatibility>,
+    pub root_incompatibility: usize,
+    pub origins: Vec<ConstraintOrigin>,
+    pub suggestions: Vec<ResolutionSuggestion>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExplainedResolution {
+    pub success: bool,
+    pub resolution: Option<Resolution>,
+    pub explanation: Option<ResolutionFailureExplanation>,
+    pub decision_log: Option<Vec<DecisionLogEntry>>,
+}
+
+#[derive(Debug, Clone)]
+struct Requirement {
+    constraint: VersionConstraint,
+    required_by: Option<(String, Version)>,
+    incompatibility: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+struct SearchState {
+    selected: HashMap<String, Version>,
+    requirements: BTreeMap<String, Vec<Requirement>>,
+}
+
+enum SolveError {
+    Conflict(usize),
+    Exhausted,
+}
+
+struct ExplainContext<'a> {
+    resolver: &'a Resolver,
+    graph: Vec<Incompatibility>,
+    log: Vec<DecisionLogEntry>,
+    steps: usize,
+    /// First unsatisfiable constraint set encountered (package, constraints).
+    leaf: Option<(String, Vec<Requirement>)>,
+}
+
+fn describe_requirement(package: &str, req: &Requirement) -> String {
+    match &req.required_by {
+        Some((by, version)) => format!(
+            "{} {} (required by {} {})",
+            package, req.constraint, by, version
+        ),
+        None => format!("{} {} (requested)", package, req.constraint),
+    }
+}
+
+fn combined_constraint(reqs: &[Requirement]) -> String {
+    let parts: Vec<String> = reqs.iter().map(|r| r.constraint.to_string()).collect();
+    if parts.is_empty() {
+        "*".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+impl ExplainContext<'_> {
+    fn add(
+        &mut self,
+        package: &str,
+        constraint: String,
+        cause: IncompatibilityCause,
+        message: String,
+    ) -> usize {
+        let id = self.graph.len();
+        self.graph.push(Incompatibility {
+            id,
+            package: package.to_string(),
+            constraint,
+            cause,
+            message,
+        });
+        id
+    }
+
+    fn record(
+        &mut self,
+        kind: DecisionKind,
+        package: &str,
+        version: Option<&Version>,
+        message: String,
+    ) {
+        self.log.push(DecisionLogEntry {
+            step: self.log.len(),
+            kind,
+            package: package.to_string(),
+            version: version.map(|v| v.to_string()),
+            message,
+        });
+    }
+
+    fn fail_leaf(&mut self, package: &str, reqs: Vec<Requirement>, id: usize) -> SolveError {
+        let message = self.graph[id].message.clone();
+        self.record(DecisionKind::Conflict, package, None, message);
+        if self.leaf.is_none() {
+            self.leaf = Some((package.to_string(), reqs));
+        }
+        SolveError::Conflict(id)
+    }
+
+    fn candidates(&self, name: &str, reqs: &[Requirement]) -> Vec<Version> {
+        let mut versions: Vec<Version> = self
+            .resolver
+            .available
+            .get(name)
+            .map(|pkgs| pkgs.iter().map(|p| p.version.clone()).collect())
+            .unwrap_or_default();
+        versions.sort_by(|a, b| b.cmp(a));
+        versions.dedup();
+        versions.retain(|v| reqs.iter().all(|r| r.constraint.matches(v)));
+
+        if let Some(locked) = self.resolver.locked.get(name) {
+            if let Some(pos) = versions.iter().position(|v| v == locked) {
+                let locked = versions.remove(pos);
+                versions.insert(0, locked);
+            }
+        }
+        versions
+    }
+
+    fn solve(&mut self, state: &SearchState) -> Result<SearchState, SolveError> {
+        let next = state
+            .requirements
+            .keys()
+            .find(|name| !state.selected.contains_key(*name))
+            .cloned();
+        let Some(name) = next else {
+            return Ok(state.clone());
+        };
+        let reqs = state.requirements[&name].clone();
+        let constraint = combined_constraint(&reqs);
+
+        if !self.resolver.available.contains_key(&name) {
+            let id = self.add(
+                &name,
+                constraint,
+                IncompatibilityCause::PackageNotFound,
+                format!("{} is not available from any provider", name),
+            );
+            return Err(self.fail_leaf(&name, reqs, id));
+        }
+
+        let candidates = self.candidates(&name, &reqs);
+        if candidates.is_empty() {
+            let (cause, message) = if reqs.len() == 1 {
+                (
+                    IncompatibilityCause::NoVersions,
+                    format!(
+                        "no version of {} matches {}",
+                        name,
+                        describe_requirement(&name, &reqs[0])
+                    ),
+                )
+            } else {
+                let described: Vec<String> = reqs
+                    .iter()
+                    .map(|r| describe_requirement(&name, r))
+                    .collect();
+                (
+                    IncompatibilityCause::Conflict {
+                        causes: reqs.iter().map(|r| r.incompatibility).collect(),
+                    },
+                    format!(
+                        "no version of {} satisfies all of: {}",
+                        name,
+                        described.join("; ")
+                    ),
+                )
+            };
+            let id = self.add(&name, constraint, cause, message);
+            return Err(self.fail_leaf(&name, reqs, id));
+        }
+
+        let mut failures = Vec::new();
+        for version in candidates {
+            self.steps += 1;
+            if self.steps > MAX_RESOLVE_STEPS {
+                return Err(SolveError::Exhausted);
+            }
+            self.record(
+                DecisionKind::Decide,
+                &name,
+                Some(&version),
+                format!("selecting {} {}", name, version),
+            );
+
+            match self.try_version(state, &name, &version) {
+                Ok(solved) => return Ok(solved),
+                Err(SolveError::Conflict(id)) => {
+                    failures.push(id);
+                    self.record(
+                        DecisionKind::Backtrack,
+                        &name,
+                        Some(&version),
+                        format!("{} {} rejected: {}", name, version, self.graph[id].message),
+                    );
+                }
+                Err(SolveError::Exhausted) => return Err(SolveError::Exhausted),
+            }
+        }
+
+        let id = self.add(
+            &name,
+            constraint.clone(),
+            IncompatibilityCause::Derived { causes: failures },
+            format!(
+                "no version of {} matching {} leads to a solution",
+                name, constraint
+            ),
+        );
+        Err(SolveError::Conflict(id))
+    }
+
+    fn try_version(
+        &mut self,
+        state: &SearchState,
+        name: &str,
+        version: &Version,
+    ) -> Result<SearchState, SolveError> {
+        let mut next = state.clone();
+        next.selected.insert(name.to_string(), version.clone());
+
+        let dependencies = self
+            .resolver
+            .available
+            .get(name)
+            .and_then(|pkgs| pkgs.iter().find(|p| &p.version == version))
+            .map(|p| p.dependencies.clone())
+            .unwrap_or_default();
+
+        for dep in dependencies {
+            let dep_id = self.add(
+                &dep.name,
+                dep.constraint.to_string(),
+                IncompatibilityCause::Dependency {
+                    package: name.to_string(),
+                    version: version.to_string(),
+                },
+                format!(
+                    "{} {} depends on {} {}",
+                    name, version, dep.name, dep.constraint
+                ),
+            );
+            let requirement = Requirement {
+                constraint: dep.constraint.clone(),
+                required_by: Some((name.to_string(), version.clone())),
+                incompatibility: dep_id,
+            };
+            let existing = next.requirements.entry(dep.name.clone()).or_default();
+            existing.push(requirement);
+
+            if let Some(selected) = next.selected.get(&dep.name) {
+                if !dep.constraint.matches(selected) {
+                    let reqs = existing.clone();
+                    let id = self.add(
+                        &dep.name,
+                        combined_constraint(&reqs),
+                        IncompatibilityCause::Conflict {
+                            causes: reqs.iter().map(|r| r.incompatibility).collect(),
+                        },
+                        format!(
+                            "{} {} requires {} {}, but {} {} is already selected",
+                            name, version, dep.name, dep.constraint, dep.name, selected
+                        ),
+                    );
+                    return Err(self.fail_leaf(&dep.name, reqs, id));
+                }
+            }
+        }
+
+        self.solve(&next)
+    }
+
+    fn derivation(&self, id: usize, visited: &mut HashSet<usize>, out: &mut Vec<String>) {
+        if !visited.insert(id) {
+            return;
+        }
+        let node = &self.graph[id];
+        match &node.cause {
+            IncompatibilityCause::Conflict { causes }
+            | IncompatibilityCause::Derived { causes }
+                if !causes.is_empty() =>
+            {
+                for cause in causes {
+                    self.derivation(*cause, visited, out);
+                }
+                let reasons: Vec<&str> = causes
+                    .iter()
+                    .map(|c| self.graph[*c].message.as_str())
+                    .collect();
+                out.push(format!(
+                    "Because {}, {}.",
+                    reasons.join(" and "),
+                    node.message
+                ));
+            }
+            _ => out.push(format!("{}.", node.message)),
+        }
+    }
+
+    fn suggestions(&self, package: &str, reqs: &[Requirement]) -> Vec<ResolutionSuggestion> {
+        let mut suggestions = Vec::new();
+        let mut versions: Vec<&Version> = self
+            .resolver
+            .available
+            .get(package)
+            .map(|pkgs| pkgs.iter().map(|p| &p.version).collect())
+            .unwrap_or_default();
+        versions.sort_by(|a, b| b.cmp(a));
+        versions.dedup();
+
+        for (i, req) in reqs.iter().enumerate() {
+            let compatible_with_others: Vec<&Version> = versions
+                .iter()
+                .copied()
+                .filter(|v| {
+                    reqs.iter()
+                        .enumerate()
+                        .all(|(j, other)| i == j || other.constraint.matches(v))
+                })
+                .collect();
+
+            match &req.required_by {
+                None => {
+                    if let Some(best) = compatible_with_others.first() {
+                        suggestions.push(ResolutionSuggestion {
+                            action: SuggestionAction::Relax,
+                            package: package.to_string(),
+                            constraint: format!("={}", best),
+                            message: format!(
+                                "relax the requested {} {} to allow {}",
+                                package, req.constraint, best
+                            ),
+                        });
+                    }
+                }
+                Some((by, by_version)) => {
+                    let mut alternatives: Vec<&Package> = self
+                        .resolver
+                        .available
+                        .get(by)
+                        .map(|pkgs| pkgs.iter().filter(|p| &p.version != by_version).collect())
+                        .unwrap_or_default();
+                    alternatives.sort_by(|a, b| b.version.cmp(&a.version));
+
+                    for alt in alternatives {
+                        let dep = alt.dependencies.iter().find(|d| d.name == package);
+                        let fits = match dep {
+                            None => true,
+                            Some(d) => compatible_with_others
+                                .iter()
+                                .any(|v| d.constraint.matches(v)),
+                        };
+                        if !fits {
+                            continue;
+                        }
+                        let reason = match dep {
+                            Some(d) => format!("requires {} {}", package, d.constraint),
+                            None => format!("no longer depends on {}", package),
+                        };
+                        let (action, constraint, verb) = if alt.version > *by_version {
+                            (
+                                SuggestionAction::Upgrade,
+                                format!(">={}", alt.version),
+                                "upgrade",
+                            )
+                        } else {
+                            (SuggestionAction::Pin, format!("={}", alt.version), "pin")
+                        };
+                        suggestions.push(ResolutionSuggestion {
+                            action,
+                            package: by.clone(),
+                            message: format!("{} {} to {} ({})", verb, by, constraint, reason),
+                            constraint,
+                        });
+                        break;
+                    }
+                }
+            }
+        }
+
+        suggestions
+    }
+}
+
+impl Resolver {
+    /// Resolve with full backtracking and, on failure, return a structured
+    /// explanation of the clashing constraints instead of a flat error.
+    ///
+    /// When `include_log` is set the decision log is attached on both success
+    /// and failure.
+    pub fn resolve_explained(
+        &self,
+        root_deps: &[Dependency],
+        include_log: bool,
+    ) -> CogniaResult<ExplainedResolution> {
+        let mut ctx = ExplainContext {
+            resolver: self,
+            graph: Vec::new(),
+            log: Vec::new(),
+            steps: 0,
+            leaf: None,
+        };
+
+        let mut state = SearchState::default();
+        for dep in root_deps {
+            let id = ctx.add(
+                &dep.name,
+                dep.constraint.to_string(),
+                IncompatibilityCause::Root,
+                format!("{} {} is requested", dep.name, dep.constraint),
+            );
+            state
+                .requirements
+                .entry(dep.name.clone())
+                .or_default()
+                .push(Requirement {
+                    constraint: dep.constraint.clone(),
+                    required_by: None,
+                    incompatibility: id,
+                });
+        }
+
+        let outcome = ctx.solve(&state);
+        let decision_log = include_log.then(|| ctx.log.clone());
+
+        match outcome {
+            Ok(solved) => Ok(ExplainedResolution {
+                success: true,
+                resolution: Some(Resolution {
+                    packages: solved.selected,
+                }),
+                explanation: None,
+                decision_log,
+            }),
+            Err(SolveError::Exhausted) => Err(CogniaError::Resolution(format!(
+                "Dependency resolution gave up after {} decisions",
+                MAX_RESOLVE_STEPS
+            ))),
+            Err(SolveError::Conflict(root_id)) => {
+                let (package, reqs) = ctx
+                    .leaf
+                    .clone()
+                    .unwrap_or_else(|| (ctx.graph[root_id].package.clone(), Vec::new()));
+
+                let mut derivation = Vec::new();
+                ctx.derivation(root_id, &mut HashSet::new(), &mut derivation);
+
+                let origins = reqs
+                    .iter()
+                    .map(|r| ConstraintOrigin {
+                        package: package.clone(),
+                        constraint: r.constraint.to_string(),
+                        required_by: r.required_by.as_ref().map(|(by, _)| by.clone()),
+                        required_by_version: r.required_by.as_ref().map(|(_, v)| v.to_string()),
+                        incompatibility: r.incompatibility,
+                    })
+                    .collect();
+                let suggestions = ctx.suggestions(&package, &reqs);
+                let summary = match reqs.len() {
+                    0 => ctx.graph[root_id].message.clone(),
+                    _ => format!(
+                        "Could not find a version of {} satisfying {}",
+                        package,
+                        reqs.iter()
+                            .map(|r| describe_requirement(&package, r))
+                            .collect::<Vec<_>>()
+                            .join(" and ")
+                    ),
+                };
+
+                Ok(ExplainedResolution {
+                    success: false,
+                    resolution: None,
+                    explanation: Some(ResolutionFailureExplanation {
+                        conflicting_package: package,
+                        summary,
+                        derivation,
+                        incompatibilities: ctx.graph,
+                        root_incompatibility: root_id,
+                        origins,
+                        suggestions,
+                    }),
+                    decision_log,
+                })
+            }
+        }
+    }
+}
+
 pub fn explain_conflict(conflicts: &[ConflictExplanation]) -> String {
     let mut msg = String::from("Dependency resolution failed:\n\n");
 
@@ -567,4 +1104,177 @@ fn test_duplicate_root_deps_same_package() {
         let result = resolver.resolve(&deps).unwrap();
         assert_eq!(result.get("a"), Some(&"1.0.0".parse().unwrap()));
     }
+
+    // --- resolve_explained ---
+
+    fn root(name: &str, constraint: &str) -> Dependency {
+        Dependency {
+            name: name.into(),
+            constraint: constraint.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_explained_backtracks_to_compatible_version() {
+        // c 2.0 needs d ^2 which clashes with b; c 1.0 is compatible
+        let mut resolver = Resolver::new();
+        resolver.add_package(make_pkg("b", "1.0.0", vec![("d", "^1.0.0")]));
+        resolver.add_package(make_pkg("c", "1.0.0", vec![("d", "^1.0.0")]));
+        resolver.add_package(make_pkg("c", "2.0.0", vec![("d", "^2.0.0")]));
+        resolver.add_package(make_pkg("d", "1.0.0", vec![]));
+        resolver.add_package(make_pkg("d", "2.0.0", vec![]));
+
+        let result = resolver
+            .resolve_explained(&[root("b", "*"), root("c", "*")], true)
+            .unwrap();
+        assert!(result.success);
+        let resolution = result.resolution.unwrap();
+        assert_eq!(resolution.get("c"), Some(&"1.0.0".parse().unwrap()));
+        assert_eq!(resolution.get("d"), Some(&"1.0.0".parse().unwrap()));
+
+        let log = result.decision_log.unwrap();
+        assert!(log.iter().any(|e| e.kind == DecisionKind::Backtrack));
+    }
+
+    #[test]
+    fn test_resolve_agrees_with_explained() {
+        let mut resolver = Resolver::new();
+        resolver.add_package(make_pkg("b", "1.0.0", vec![("d", "^1.0.0")]));
+        resolver.add_package(make_pkg("c", "1.0.0", vec![("d", "^1.0.0")]));
+        resolver.add_package(make_pkg("c", "2.0.0", vec![("d", "^2.0.0")]));
+        resolver.add_package(make_pkg("d", "1.0.0", vec![]));
+        resolver.add_package(make_pkg("d", "2.0.0", vec![]));
+
+        let resolution = resolver.resolve(&[root("b", "*"), root("c", "*")]).unwrap();
+        assert_eq!(resolution.get("c"), Some(&"1.0.0".parse().unwrap()));
+
+        let deps = [root("b", "*"), root("c", "^2.0.0")];
+        let err = resolver.resolve(&deps).unwrap_err();
+        let explanation = resolver
+            .resolve_explained(&deps, false)
+            .unwrap()
+            .explanation
+            .unwrap();
+        assert!(matches!(err, CogniaError::Conflict(_)));
+        assert!(err.to_string().contains(&explanation.summary));
+    }
+
+    #[test]
+    fn test_explained_diamond_conflict() {
+        // a -> b ^1, c ^1; b -> d ^1; c -> d ^2
+        let mut resolver = Resolver::new();
+        resolver.add_package(make_pkg(
+            "a",
+            "1.0.0",
+            vec![("b", "^1.0.0"), ("c", "^1.0.0")],
+        ));
+        resolver.add_package(make_pkg("b", "1.0.0", vec![("d", "^1.0.0")]));
+        resolver.add_package(make_pkg("b", "2.0.0", vec![("d", "^2.0.0")]));
+        resolver.add_package(make_pkg("c", "1.0.0", vec![("d", "^2.0.0")]));
+        resolver.add_package(make_pkg("d", "1.0.0", vec![]));
+        resolver.add_package(make_pkg("d", "2.0.0", vec![]));
+
+        let result = resolver
+            .resolve_explained(&[root("a", "*")], false)
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.decision_log.is_none());
+
+        let explanation = result.explanation.unwrap();
+        assert_eq!(explanation.conflicting_package, "d");
+        let requirers: Vec<_> = explanation
+            .origins
+            .iter()
+            .filter_map(|o| o.required_by.clone())
+            .collect();
+        assert!(requirers.contains(&"b".to_string()));
+        assert!(requirers.contains(&"c".to_string()));
+
+        // b 2.0 would agree with c on d ^2
+        assert!(explanation
+            .suggestions
+            .iter()
+            .any(|s| s.action == SuggestionAction::Upgrade && s.package == "b"));
+
+        let root_node = &explanation.incompatibilities[explanation.root_incompatibility];
+        assert_eq!(root_node.package, "a");
+        assert!(matches!(
+            root_node.cause,
+            IncompatibilityCause::Derived { .. }
+        ));
+        assert!(explanation
+            .derivation
+            .last()
+            .unwrap()
+            .starts_with("Because"));
+    }
+
+    #[test]
+    fn test_explained_no_matching_version_suggests_relax() {
+        let mut resolver = Resolver::new();
+        resolver.add_package(make_pkg("a", "1.0.0", vec![]));
+        resolver.add_package(make_pkg("a", "1.2.0", vec![]));
+
+        let result = resolver
+            .resolve_explained(&[root("a", "^5.0.0")], false)
+            .unwrap();
+        let explanation = result.explanation.unwrap();
+        assert_eq!(explanation.conflicting_package, "a");
+        assert!(matches!(
+            explanation.incompatibilities[explanation.root_incompatibility].cause,
+            IncompatibilityCause::NoVersions
+        ));
+        assert_eq!(explanation.suggestions.len(), 1);
+        assert_eq!(explanation.suggestions[0].action, SuggestionAction::Relax);
+        assert!(explanation.suggestions[0].message.contains("1.2.0"));
+    }
+
+    #[test]
+    fn test_explained_package_not_found() {
+        let resolver = Resolver::new();
+        let result = resolver
+            .resolve_explained(&[root("ghost", "*")], true)
+            .unwrap();
+        assert!(!result.success);
+        let explanation = result.explanation.unwrap();
+        assert!(matches!(
+            explanation.incompatibilities[explanation.root_incompatibility].cause,
+            IncompatibilityCause::PackageNotFound
+        ));
+        assert!(explanation.suggestions.is_empty());
+        assert!(!result.decision_log.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_explained_respects_locked_version() {
+        let mut resolver = Resolver::new();
+        resolver.add_package(make_pkg("a", "1.0.0", vec![]));
+        resolver.add_package(make_pkg("a", "2.0.0", vec![]));
+        resolver.lock_version("a", "1.0.0".parse().unwrap());
+
+        let result = resolver
+            .resolve_explained(&[root("a", "*")], false)
+            .unwrap();
+        assert_eq!(
+            result.resolution.unwrap().get("a"),
+            Some(&"1.0.0".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_explanation_serializes_cause_tag() {
+        let node = Incompatibility {
+            id: 0,
+            package: "a".into(),
+            constraint: "*".into(),
+            cause: IncompatibilityCause::Dependency {
+                package: "b".into(),
+                version: "1.0.0".into(),
+            },
+            message: "b 1.0.0 depends on a *".into(),
+        };
+        let json = serde_json::to_value(&node).unwrap();
+        assert_eq!(json["cause"]["kind"], "dependency");
+        assert_eq!(json["cause"]["package"], "b");
+    }
 }
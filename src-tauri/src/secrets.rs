@@ -1,5 +1,6 @@
 use crate::config::Settings;
 use crate::platform::fs;
+use crate::platform::secrets::SecretStore;
 use std::path::PathBuf;
 use tauri_plugin_stronghold::kdf::KeyDerivation;
 use tauri_plugin_stronghold::stronghold::Stronghold;
@@ -8,6 +9,8 @@
 const SALT_FILE: &str = "secrets.salt";
 const CLIENT_NAME: &[u8] = b"cognia-launcher";
 pub const KNOWN_SECRET_PROVIDERS: [&str; 2] = ["github", "gitlab"];
+/// Secret store namespace holding provider tokens
+pub const PROVIDER_SECRET_NAMESPACE: &str = "providers";
 
 fn snapshot_file_name() -> &'static str {
     SNAPSHOT_FILE
@@ -21,6 +24,11 @@ pub fn provider_secret_key(provider: &str) -> String {
     format!("providers.{provider}.token")
 }
 
+/// Key of a provider token within [`PROVIDER_SECRET_NAMESPACE`]
+pub fn provider_store_key(provider: &str) -> String {
+    format!("{provider}.token")
+}
+
 pub fn provider_env_var(provider: &str) -> Option<&'static str> {
     match provider {
         "github" => Some("GITHUB_TOKEN"),
@@ -216,36 +224,65 @@ pub fn remove_secret(&self, key: &str) -> Result<bool, String> {
 
         Ok(existed)
     }
+}
 
-    pub fn migrate_legacy_provider_tokens(
-        &self,
-        settings: &mut Settings,
-    ) -> Result<Vec<String>, String> {
-        let mut migrated = Vec::new();
-
-        for provider in KNOWN_SECRET_PROVIDERS {
-            let Some(legacy_token) = settings.get_provider_legacy_token(provider) else {
-                continue;
-            };
+/// Move provider tokens from their old homes into `store`: plaintext
+/// tokens in the settings file, and tokens in the password vault when it
+/// is unlocked. The old copies are removed once the store holds the token,
+/// and a token already in the store is never overwritten. Returns the
+/// providers whose settings changed.
+pub async fn migrate_provider_secrets(
+    settings: &mut Settings,
+    vault: &SecretVault,
+    store: &SecretStore,
+) -> Result<Vec<String>, String> {
+    let mut migrated = Vec::new();
+
+    for provider in KNOWN_SECRET_PROVIDERS {
+        let store_key = provider_store_key(provider);
+        let vault_key = provider_secret_key(provider);
+        let legacy_token = normalized_secret(settings.get_provider_legacy_token(provider));
+        let vault_token = if vault.is_unlocked() {
+            normalized_secret(vault.get_secret(&vault_key)?)
+        } else {
+            None
+        };
+        if legacy_token.is_none() && vault_token.is_none() {
+            continue;
+        }
 
-            let key = provider_secret_key(provider);
-            if !self.contains_secret(&key)? {
-                self.save_secret(&key, &legacy_token)?;
+        let stored = store
+            .contains(PROVIDER_SECRET_NAMESPACE, &store_key)
+            .await
+            .map_err(|error| error.to_string())?;
+        if !stored {
+            if let Some(token) = vault_token.as_ref().or(legacy_token.as_ref()) {
+                store
+                    .set(PROVIDER_SECRET_NAMESPACE, &store_key, token)
+                    .await
+                    .map_err(|error| error.to_string())?;
             }
-
-            settings.clear_provider_legacy_token(provider);
-            settings.set_provider_secret_saved(provider, true);
-            migrated.push(provider.to_string());
         }
 
-        Ok(migrated)
+        if vault_token.is_some() {
+            vault.remove_secret(&vault_key)?;
+        }
+        settings.clear_provider_legacy_token(provider);
+        settings.set_provider_secret_saved(provider, true);
+        migrated.push(provider.to_string());
     }
+
+    Ok(migrated)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{provider_secret_key, SecretVault};
+    use super::{
+        migrate_provider_secrets, provider_secret_key, provider_store_key, SecretVault,
+        PROVIDER_SECRET_NAMESPACE,
+    };
     use crate::config::Settings;
+    use crate::platform::secrets::SecretStore;
     use tempfile::tempdir;
 
     #[tokio::test]
@@ -277,9 +314,10 @@ async fn setup_save_read_remove_and_reset_round_trip() {
     }
 
     #[tokio::test]
-    async fn migration_moves_legacy_tokens_without_overwriting_existing_secrets() {
+    async fn migration_moves_tokens_into_store_and_removes_old_copies() {
         let temp = tempdir().unwrap();
         let mut vault = SecretVault::new(temp.path().to_path_buf());
+        let store = SecretStore::encrypted_file(temp.path().to_path_buf());
         let mut settings = Settings::default();
 
         settings
@@ -291,29 +329,43 @@ async fn migration_moves_legacy_tokens_without_overwriting_existing_secrets() {
 
         vault.setup("secret-passphrase").await.unwrap();
         vault
-            .save_secret(&provider_secret_key("github"), "ghp_existing")
+            .save_secret(&provider_secret_key("github"), "ghp_vault")
+            .unwrap();
+        store
+            .set(
+                PROVIDER_SECRET_NAMESPACE,
+                &provider_store_key("gitlab"),
+                "glpat_existing",
+            )
+            .await
             .unwrap();
 
-        let migrated = vault.migrate_legacy_provider_tokens(&mut settings).unwrap();
+        let migrated = migrate_provider_secrets(&mut settings, &vault, &store)
+            .await
+            .unwrap();
         assert_eq!(migrated, vec!["github".to_string(), "gitlab".to_string()]);
 
-        assert_eq!(
-            vault
-                .get_secret(&provider_secret_key("github"))
-                .unwrap()
-                .as_deref(),
-            Some("ghp_existing")
-        );
-        assert_eq!(
-            vault
-                .get_secret(&provider_secret_key("gitlab"))
-                .unwrap()
-                .as_deref(),
-            Some("glpat_legacy")
-        );
+        for (provider, expected) in [("github", "ghp_vault"), ("gitlab", "glpat_existing")] {
+            let stored = store
+                .get(PROVIDER_SECRET_NAMESPACE, &provider_store_key(provider))
+                .await
+                .unwrap();
+            assert_eq!(stored.as_deref(), Some(expected));
+        }
+        assert!(!vault
+            .contains_secret(&provider_secret_key("github"))
+            .unwrap());
         assert_eq!(settings.get_provider_legacy_token("github"), None);
         assert_eq!(settings.get_provider_legacy_token("gitlab"), None);
         assert!(settings.get_provider_secret_saved("github"));
         assert!(settings.get_provider_secret_saved("gitlab"));
+
+        let toml = toml::to_string(&settings).unwrap();
+        assert!(!toml.contains("legacy"));
+
+        let migrated = migrate_provider_secrets(&mut settings, &vault, &store)
+            .await
+            .unwrap();
+        assert!(migrated.is_empty());
     }
 }
@@ -8,12 +8,16 @@
 //! - System notifications
 //! - Autostart management
 
+use crate::commands::download::SharedDownloadManager;
+use crate::commands::plugin::SharedPluginManager;
+use crate::download::{DownloadState, DownloadTask};
 use crate::SharedSettings;
 use log::info;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tauri::{
     image::Image,
     menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu},
@@ -27,6 +31,11 @@
 
 pub const TRAY_ICON_ID: &str = "cognia-tray";
 const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Maximum number of individual downloads listed in the Downloads submenu
+const MAX_TRAY_DOWNLOAD_ITEMS: usize = 5;
+/// Minimum interval between Downloads submenu rebuilds
+const TRAY_DOWNLOAD_REFRESH_INTERVAL: Duration = Duration::from_millis(1500);
+const TRAY_DOWNLOAD_NAME_MAX_CHARS: usize = 32;
 
 /// Tray icon state representation
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -48,6 +57,15 @@ pub enum TrayLanguage {
     Zh,
 }
 
+impl TrayLanguage {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TrayLanguage::En => "en",
+            TrayLanguage::Zh => "zh",
+        }
+    }
+}
+
 /// Tray left-click behavior
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
@@ -237,6 +255,9 @@ pub struct TrayState {
     pub menu_config: TrayMenuConfig,
     pub terminal_default_profile_id: Option<String>,
     pub terminal_recent_profiles: Vec<TrayTerminalProfileEntry>,
+    pub download_entries: Vec<TrayDownloadEntry>,
+    /// Unread entries in the notification center
+    pub unread_notifications: usize,
 }
 
 impl Default for TrayState {
@@ -260,6 +281,8 @@ fn default() -> Self {
             menu_config: TrayMenuConfig::default(),
             terminal_default_profile_id: None,
             terminal_recent_profiles: Vec::new(),
+            download_entries: Vec::new(),
+            unread_notifications: 0,
         }
     }
 }
@@ -273,6 +296,19 @@ pub struct TrayTerminalProfileEntry {
     pub name: String,
 }
 
+/// One download listed in the tray Downloads submenu. Values are coarsened so
+/// small progress/speed fluctuations don't force a menu rebuild.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrayDownloadEntry {
+    pub id: String,
+    pub name: String,
+    pub percent: u8,
+    /// Bytes per second, rounded down to whole KiB
+    pub speed: u64,
+    pub paused: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct TrayTerminalLaunchPayload {
@@ -324,6 +360,10 @@ struct MenuLabels {
     downloads_pause_all: &'static str,
     downloads_resume_all: &'static str,
     downloads_open_page: &'static str,
+    downloads_pause: &'static str,
+    downloads_resume: &'static str,
+    downloads_cancel: &'static str,
+    downloads_paused: &'static str,
 }
 
 impl MenuLabels {
@@ -371,6 +411,10 @@ fn for_language(lang: TrayLanguage) -> Self {
                 downloads_pause_all: "Pause All",
                 downloads_resume_all: "Resume All",
                 downloads_open_page: "Open Downloads Page",
+                downloads_pause: "Pause",
+                downloads_resume: "Resume",
+                downloads_cancel: "Cancel",
+                downloads_paused: "paused",
             },
             TrayLanguage::Zh => Self {
                 show_window: "显示窗口",
@@ -414,6 +458,10 @@ fn for_language(lang: TrayLanguage) -> Self {
                 downloads_pause_all: "全部暂停",
                 downloads_resume_all: "全部恢复",
                 downloads_open_page: "打开下载页面",
+                downloads_pause: "暂停",
+                downloads_resume: "继续",
+                downloads_cancel: "取消",
+                downloads_paused: "已暂停",
             },
         }
     }
@@ -424,7 +472,7 @@ fn get_tooltip(state: &TrayState) -> String {
     let active_downloads = state.active_downloads.load(Ordering::SeqCst);
     let base = format!("CogniaLauncher v{}", APP_VERSION);
 
-    match state.language {
+    let tooltip = match state.language {
         TrayLanguage::En => {
             if active_downloads > 0 {
                 format!("{} | {} active download(s)", base, active_downloads)
@@ -447,6 +495,12 @@ fn get_tooltip(state: &TrayState) -> String {
                 base
             }
         }
+    };
+
+    match (state.unread_notifications, state.language) {
+        (0, _) => tooltip,
+        (count, TrayLanguage::En) => format!("{} | {} unread notification(s)", tooltip, count),
+        (count, TrayLanguage::Zh) => format!("{} | {} 条未读通知", tooltip, count),
     }
 }
 
@@ -585,6 +639,11 @@ fn apply_visual_state_to_tray<R: Runtime>(
         tray.set_tooltip(Some(&tooltip))
             .map_err(|e| e.to_string())?;
     }
+    if let Some(window) = app.get_webview_window("main") {
+        let badge = (state.unread_notifications > 0).then_some(state.unread_notifications as i64);
+        // Not every platform supports badges; the tooltip still carries the count
+        let _ = window.set_badge_count(badge);
+    }
     Ok(())
 }
 
@@ -599,6 +658,37 @@ fn refresh_tray_visual_state<R: Runtime>(app: &AppHandle<R>) -> Result<(), Strin
     Ok(())
 }
 
+/// Whether the tray notification toggles allow a desktop toast for `event`.
+/// Always true when the tray is not set up (e.g. headless runs).
+pub(crate) async fn toast_allowed<R: Runtime>(
+    app: &AppHandle<R>,
+    event: Option<TrayNotificationEvent>,
+    important: bool,
+) -> bool {
+    let Some(state) = app.try_state::<SharedTrayState>() else {
+        return true;
+    };
+    let guard = state.read().await;
+    should_send_notification(
+        guard.show_notifications,
+        guard.notification_level,
+        &guard.notification_events,
+        event,
+        Some(important),
+    )
+}
+
+/// Update the unread notification count shown in the tray tooltip and badge
+pub(crate) async fn set_unread_notifications<R: Runtime>(
+    app: &AppHandle<R>,
+    count: usize,
+) -> Result<(), String> {
+    if let Some(state) = app.try_state::<SharedTrayState>() {
+        state.write().await.unread_notifications = count;
+    }
+    refresh_tray_visual_state(app)
+}
+
 fn should_send_notification(
     show_notifications: bool,
     notification_level: TrayNotificationLevel,
@@ -753,11 +843,75 @@ fn build_terminal_submenu<R: Runtime>(
     Ok(Some(submenu))
 }
 
-/// Build a Downloads submenu with dynamic status
+/// Collect the downloads shown in the tray: in-flight first, then queued, then
+/// paused, capped at [`MAX_TRAY_DOWNLOAD_ITEMS`].
+fn tray_download_entries(tasks: &[DownloadTask]) -> Vec<TrayDownloadEntry> {
+    let rank = |state: &DownloadState| match state {
+        DownloadState::Downloading => 0,
+        DownloadState::Queued => 1,
+        _ => 2,
+    };
+
+    let mut visible: Vec<&DownloadTask> = tasks
+        .iter()
+        .filter(|task| task.state.is_active() || task.state == DownloadState::Paused)
+        .collect();
+    visible.sort_by(|a, b| {
+        rank(&a.state)
+            .cmp(&rank(&b.state))
+            .then(b.priority.cmp(&a.priority))
+            .then(a.created_at.cmp(&b.created_at))
+    });
+
+    visible
+        .into_iter()
+        .take(MAX_TRAY_DOWNLOAD_ITEMS)
+        .map(|task| TrayDownloadEntry {
+            id: task.id.clone(),
+            name: task.name.clone(),
+            percent: task.progress.percent.clamp(0.0, 100.0) as u8,
+            speed: (task.progress.speed.max(0.0) as u64 / 1024) * 1024,
+            paused: task.state == DownloadState::Paused,
+        })
+        .collect()
+}
+
+fn tray_download_label(entry: &TrayDownloadEntry, labels: &MenuLabels) -> String {
+    let name = if entry.name.chars().count() > TRAY_DOWNLOAD_NAME_MAX_CHARS {
+        let truncated: String = entry
+            .name
+            .chars()
+            .take(TRAY_DOWNLOAD_NAME_MAX_CHARS - 1)
+            .collect();
+        format!("{}…", truncated)
+    } else {
+        entry.name.clone()
+    };
+
+    if entry.paused {
+        format!(
+            "{} — {}% ({})",
+            name, entry.percent, labels.downloads_paused
+        )
+    } else {
+        format!(
+            "{} — {}% · {}/s",
+            name,
+            entry.percent,
+            crate::platform::disk::format_size(entry.speed)
+        )
+    }
+}
+
+/// Build a Downloads submenu with dynamic status.
+///
+/// Per-download item IDs are derived from the task ID only, so they stay stable
+/// across rebuilds and a click always targets the right task.
 fn build_downloads_submenu<R: Runtime>(
     app: &AppHandle<R>,
     labels: &MenuLabels,
     active_downloads: usize,
+    entries: &[TrayDownloadEntry],
 ) -> Result<Submenu<R>, Box<dyn std::error::Error>> {
     let status_text = if active_downloads > 0 {
         format!("{} {}", active_downloads, labels.downloads_active)
@@ -787,19 +941,58 @@ fn build_downloads_submenu<R: Runtime>(
         None::<&str>,
     )?;
 
+    let task_submenus = entries
+        .iter()
+        .map(|entry| {
+            let toggle = MenuItem::with_id(
+                app,
+                format!("download_task_toggle::{}", entry.id),
+                if entry.paused {
+                    labels.downloads_resume
+                } else {
+                    labels.downloads_pause
+                },
+                true,
+                None::<&str>,
+            )?;
+            let cancel = MenuItem::with_id(
+                app,
+                format!("download_task_cancel::{}", entry.id),
+                labels.downloads_cancel,
+                true,
+                None::<&str>,
+            )?;
+            Submenu::with_id_and_items(
+                app,
+                format!("download_task::{}", entry.id),
+                tray_download_label(entry, labels),
+                true,
+                &[&toggle, &cancel],
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let separator_top = PredefinedMenuItem::separator(app)?;
+    let separator_tasks = PredefinedMenuItem::separator(app)?;
+    let separator_bottom = PredefinedMenuItem::separator(app)?;
+
+    let mut item_refs: Vec<&dyn tauri::menu::IsMenuItem<R>> =
+        vec![&status_item, &separator_top, &pause_item, &resume_item];
+    if !task_submenus.is_empty() {
+        item_refs.push(&separator_tasks);
+        for submenu in &task_submenus {
+            item_refs.push(submenu);
+        }
+    }
+    item_refs.push(&separator_bottom);
+    item_refs.push(&open_item);
+
     let submenu = Submenu::with_id_and_items(
         app,
         "downloads_sub",
         labels.downloads_submenu,
         true,
-        &[
-            &status_item,
-            &PredefinedMenuItem::separator(app)?,
-            &pause_item,
-            &resume_item,
-            &PredefinedMenuItem::separator(app)?,
-            &open_item,
-        ],
+        &item_refs,
     )?;
     Ok(submenu)
 }
@@ -916,7 +1109,12 @@ fn build_menu<R: Runtime>(
                 if need_separator {
                     menu.append(&PredefinedMenuItem::separator(app)?)?;
                 }
-                let dl = build_downloads_submenu(app, &labels, active_downloads)?;
+                let dl = build_downloads_submenu(
+                    app,
+                    &labels,
+                    active_downloads,
+                    &state.download_entries,
+                )?;
                 menu.append(&dl)?;
                 need_separator = true;
             }
@@ -1405,11 +1603,128 @@ fn handle_menu_event<R: Runtime>(app: &AppHandle<R>, event: MenuEvent) {
         return;
     }
 
+    if let Some((control, task_id)) = tray_download_control_from_menu_id(event.id.as_ref()) {
+        control_download_task(app, task_id.to_string(), control);
+        return;
+    }
+
     if let Some(action) = tray_action_from_menu_id(event.id.as_ref()) {
         execute_tray_action(app, action);
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TrayDownloadControl {
+    /// Pause if running, resume if paused (decided at click time)
+    Toggle,
+    Cancel,
+}
+
+fn tray_download_control_from_menu_id(id: &str) -> Option<(TrayDownloadControl, &str)> {
+    let parsed = if let Some(task_id) = id.strip_prefix("download_task_toggle::") {
+        Some((TrayDownloadControl::Toggle, task_id))
+    } else {
+        id.strip_prefix("download_task_cancel::")
+            .map(|task_id| (TrayDownloadControl::Cancel, task_id))
+    };
+    parsed.filter(|(_, task_id)| !task_id.is_empty())
+}
+
+fn control_download_task<R: Runtime>(
+    app: &AppHandle<R>,
+    task_id: String,
+    control: TrayDownloadControl,
+) {
+    let Some(manager) = app
+        .try_state::<SharedDownloadManager>()
+        .map(|state| state.inner().clone())
+    else {
+        return;
+    };
+    let app = app.clone();
+
+    tauri::async_runtime::spawn(async move {
+        {
+            let manager = manager.read().await;
+            let result = match control {
+                TrayDownloadControl::Toggle => match manager.get_task(&task_id).await {
+                    Some(task) if task.state == DownloadState::Paused => {
+                        manager.resume(&task_id).await
+                    }
+                    Some(task) if task.state.can_pause() => manager.pause(&task_id).await,
+                    _ => Ok(()),
+                },
+                TrayDownloadControl::Cancel => manager.cancel(&task_id).await,
+            };
+            if let Err(e) = result {
+                log::warn!(
+                    "Tray download action {:?} failed for {}: {}",
+                    control,
+                    task_id,
+                    e
+                );
+            }
+        }
+        refresh_tray_downloads(&app).await;
+    });
+}
+
+/// Sync the Downloads submenu with the download manager, rebuilding the menu
+/// only when a visible entry changed and the submenu is enabled.
+async fn refresh_tray_downloads<R: Runtime>(app: &AppHandle<R>) {
+    let Some(tray_state) = app
+        .try_state::<SharedTrayState>()
+        .map(|state| state.inner().clone())
+    else {
+        return;
+    };
+    let Some(manager) = app
+        .try_state::<SharedDownloadManager>()
+        .map(|state| state.inner().clone())
+    else {
+        return;
+    };
+
+    if !tray_state
+        .read()
+        .await
+        .menu_config
+        .items
+        .contains(&TrayMenuItemId::Downloads)
+    {
+        return;
+    }
+
+    let tasks = manager.read().await.list_tasks().await;
+    let entries = tray_download_entries(&tasks);
+
+    let changed = {
+        let mut guard = tray_state.write().await;
+        if guard.download_entries == entries {
+            false
+        } else {
+            guard.download_entries = entries;
+            true
+        }
+    };
+
+    if changed {
+        update_menu_state(app);
+    }
+}
+
+/// Refresh the Downloads submenu on a fixed interval instead of per progress event.
+fn spawn_tray_download_refresher<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(TRAY_DOWNLOAD_REFRESH_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            refresh_tray_downloads(&app).await;
+        }
+    });
+}
+
 /// Handle tray icon click events
 fn handle_tray_click<R: Runtime>(app: &AppHandle<R>, behavior: TrayClickBehavior) {
     let quick_action = app
@@ -1468,6 +1783,8 @@ fn clone_tray_state_for_setup(state: &TrayState) -> TrayState {
         menu_config: state.menu_config.clone(),
         terminal_default_profile_id: state.terminal_default_profile_id.clone(),
         terminal_recent_profiles: state.terminal_recent_profiles.clone(),
+        download_entries: state.download_entries.clone(),
+        unread_notifications: state.unread_notifications,
     }
 }
 
@@ -1540,6 +1857,8 @@ pub fn setup_tray(app: &AppHandle<Wry>) -> Result<(), Box<dyn std::error::Error>
         })
         .build(app)?;
 
+    spawn_tray_download_refresher(app.clone());
+
     info!("System tray initialized successfully");
     Ok(())
 }
@@ -1675,6 +1994,18 @@ pub async fn tray_set_terminal_profiles(
     Ok(())
 }
 
+/// Update the tray "update available" indicator from backend code paths
+/// such as self-update checks and update channel changes.
+pub(crate) async fn set_has_update<R: Runtime>(
+    app: &AppHandle<R>,
+    has_update: bool,
+) -> Result<(), String> {
+    if let Some(state) = app.try_state::<SharedTrayState>() {
+        state.write().await.has_update = has_update;
+    }
+    refresh_tray_visual_state(app)
+}
+
 /// Set whether an update is available
 #[tauri::command]
 pub async fn tray_set_has_update(
@@ -1712,13 +2043,28 @@ pub async fn tray_set_language(
     state: State<'_, SharedTrayState>,
     language: TrayLanguage,
 ) -> Result<(), String> {
-    {
+    let changed = {
         let mut guard = state.write().await;
-        guard.language = language;
-    }
+        std::mem::replace(&mut guard.language, language) != language
+    };
 
     // Rebuild menu with new language
     update_menu_state(&app);
+
+    // Let plugins re-render their strings
+    if changed {
+        if let Some(plugins) = app.try_state::<SharedPluginManager>() {
+            plugins
+                .write()
+                .await
+                .dispatch_event(
+                    "locale_changed",
+                    &serde_json::json!({ "locale": language.as_str() }),
+                )
+                .await;
+        }
+    }
+
     tray_update_tooltip(app, state).await
 }
 
@@ -1894,6 +2240,7 @@ pub async fn tray_get_state(state: State<'_, SharedTrayState>) -> Result<TraySta
         notification_events: guard.notification_events.clone(),
         always_on_top: guard.always_on_top.load(Ordering::SeqCst),
         menu_config: guard.menu_config.clone(),
+        unread_notifications: guard.unread_notifications,
     })
 }
 
@@ -1917,6 +2264,7 @@ pub struct TrayStateInfo {
     pub notification_events: Vec<TrayNotificationEvent>,
     pub always_on_top: bool,
     pub menu_config: TrayMenuConfig,
+    pub unread_notifications: usize,
 }
 
 /// Check if autostart is enabled
@@ -1961,47 +2309,24 @@ pub fn tray_disable_autostart(app: AppHandle<Wry>) -> Result<(), String> {
     }
 }
 
-/// Send a system notification
+/// Send a system notification. It is recorded in the notification center
+/// and shown subject to the notification preferences.
 #[tauri::command]
 pub async fn tray_send_notification(
     app: AppHandle<Wry>,
-    state: State<'_, SharedTrayState>,
     title: String,
     body: String,
     important: Option<bool>,
     event: Option<TrayNotificationEvent>,
 ) -> Result<(), String> {
-    use tauri_plugin_notification::NotificationExt;
-
-    let (show_notifications, notification_level, notification_events) = {
-        let guard = state.read().await;
-        (
-            guard.show_notifications,
-            guard.notification_level,
-            guard.notification_events.clone(),
-        )
-    };
-
-    let allowed = should_send_notification(
-        show_notifications,
-        notification_level,
-        &notification_events,
+    crate::commands::notifications::notify_from_frontend(
+        &app,
+        title,
+        body,
+        important.unwrap_or(false),
         event,
-        important,
-    );
-
-    if !allowed {
-        return Ok(());
-    }
-
-    app.notification()
-        .builder()
-        .title(&title)
-        .body(&body)
-        .show()
-        .map_err(|e| e.to_string())?;
-
-    Ok(())
+    )
+    .await
 }
 
 /// Rebuild tray (useful after settings change)
@@ -2135,6 +2460,106 @@ pub async fn tray_reset_menu_config(
 mod tests {
     use super::*;
 
+    fn download_task(name: &str, state: DownloadState, percent: f32, speed: f64) -> DownloadTask {
+        let mut task = DownloadTask::new(
+            format!("https://example.com/{}", name),
+            std::path::PathBuf::from(format!("/tmp/{}", name)),
+            name.to_string(),
+        );
+        task.state = state;
+        task.progress.percent = percent;
+        task.progress.speed = speed;
+        task
+    }
+
+    #[test]
+    fn tray_download_entries_orders_filters_and_caps() {
+        let mut tasks = vec![
+            download_task("paused", DownloadState::Paused, 40.0, 0.0),
+            download_task("done", DownloadState::Completed, 100.0, 0.0),
+            download_task("queued", DownloadState::Queued, 0.0, 0.0),
+            download_task(
+                "running",
+                DownloadState::Downloading,
+                55.7,
+                1536.0 * 1024.0 + 300.0,
+            ),
+        ];
+        for i in 0..MAX_TRAY_DOWNLOAD_ITEMS {
+            tasks.push(download_task(
+                &format!("extra-{}", i),
+                DownloadState::Paused,
+                1.0,
+                0.0,
+            ));
+        }
+
+        let entries = tray_download_entries(&tasks);
+        assert_eq!(entries.len(), MAX_TRAY_DOWNLOAD_ITEMS);
+        assert_eq!(entries[0].name, "running");
+        assert_eq!(entries[0].percent, 55);
+        assert_eq!(entries[0].speed, 1536 * 1024);
+        assert_eq!(entries[1].name, "queued");
+        assert!(entries[2].paused);
+        assert!(entries.iter().all(|entry| entry.name != "done"));
+    }
+
+    #[test]
+    fn tray_download_entries_ignore_small_speed_changes() {
+        let a = tray_download_entries(&[download_task(
+            "file",
+            DownloadState::Downloading,
+            10.2,
+            2048.0,
+        )]);
+        let mut task = download_task("file", DownloadState::Downloading, 10.9, 2900.0);
+        task.id = a[0].id.clone();
+        task.created_at = chrono::Utc::now();
+        let b = tray_download_entries(&[task]);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn tray_download_control_parses_stable_ids() {
+        assert_eq!(
+            tray_download_control_from_menu_id("download_task_toggle::abc"),
+            Some((TrayDownloadControl::Toggle, "abc"))
+        );
+        assert_eq!(
+            tray_download_control_from_menu_id("download_task_cancel::abc"),
+            Some((TrayDownloadControl::Cancel, "abc"))
+        );
+        assert_eq!(
+            tray_download_control_from_menu_id("download_task_cancel::"),
+            None
+        );
+        assert_eq!(
+            tray_download_control_from_menu_id("download_pause_all"),
+            None
+        );
+    }
+
+    #[test]
+    fn tray_download_label_truncates_and_marks_paused() {
+        let labels = MenuLabels::for_language(TrayLanguage::En);
+        let mut entry = TrayDownloadEntry {
+            id: "1".into(),
+            name: "a".repeat(40),
+            percent: 12,
+            speed: 2048,
+            paused: false,
+        };
+        let label = tray_download_label(&entry, &labels);
+        assert!(label.starts_with(&format!(
+            "{}…",
+            "a".repeat(TRAY_DOWNLOAD_NAME_MAX_CHARS - 1)
+        )));
+        assert!(label.ends_with("12% · 2.00 KB/s"));
+
+        entry.paused = true;
+        assert!(tray_download_label(&entry, &labels).ends_with("(paused)"));
+    }
+
     #[test]
     fn normalize_menu_items_dedupes_and_appends_quit() {
         let normalized = normalize_menu_items(&[
@@ -2312,6 +2737,19 @@ fn get_tooltip_uses_priority_for_english_and_chinese() {
         assert!(!tooltip_zh.contains("失败"));
     }
 
+    #[test]
+    fn get_tooltip_appends_unread_notification_count() {
+        let mut state = TrayState::default();
+        state.language = TrayLanguage::En;
+        assert!(!get_tooltip(&state).contains("unread"));
+
+        state.has_update = true;
+        state.unread_notifications = 3;
+        let tooltip = get_tooltip(&state);
+        assert!(tooltip.contains("Update available"));
+        assert!(tooltip.ends_with("3 unread notification(s)"));
+    }
+
     #[test]
     fn get_status_summary_uses_download_update_error_priority() {
         let mut state = TrayState::default();
@@ -2374,6 +2812,7 @@ fn tray_get_state_shape_includes_wsl_runtime_fields() {
             notification_events: state.notification_events.clone(),
             always_on_top: state.always_on_top.load(Ordering::SeqCst),
             menu_config: state.menu_config.clone(),
+            unread_notifications: state.unread_notifications,
         };
 
         assert_eq!(info.wsl_running_count, 1);
@@ -7,6 +7,8 @@ export interface GitHubBranchInfo {
   name: string;
   commitSha: string;
   protected: boolean;
+  /** Served from the conditional cache without using rate-limit quota */
+  fromCache?: boolean;
 }
 
 export interface GitHubTagInfo {
@@ -14,6 +16,8 @@ export interface GitHubTagInfo {
   commitSha: string;
   zipballUrl: string | null;
   tarballUrl: string | null;
+  /** Served from the conditional cache without using rate-limit quota */
+  fromCache?: boolean;
 }
 
 export interface GitHubReleaseInfo {
@@ -25,6 +29,8 @@ export interface GitHubReleaseInfo {
   prerelease: boolean;
   draft: boolean;
   assets: GitHubAssetInfo[];
+  /** Served from the conditional cache without using rate-limit quota */
+  fromCache?: boolean;
 }
 
 export interface GitHubAssetInfo {
@@ -35,6 +41,48 @@ export interface GitHubAssetInfo {
   downloadUrl: string;
   contentType: string | null;
   downloadCount: number | null;
+  /** Asset the picker would choose for this system */
+  recommended?: boolean;
+}
+
+/** Why the asset picker scored an asset the way it did */
+export type AssetVerdict =
+  | { kind: 'rule'; index: number; pattern: string }
+  | { kind: 'heuristic' }
+  | { kind: 'rosetta_fallback' }
+  | { kind: 'excluded_builtin' }
+  | { kind: 'excluded_by_user'; pattern: string }
+  | { kind: 'platform_mismatch'; detected: string }
+  | { kind: 'arch_mismatch'; detected: string };
+
+export interface AssetExplanation {
+  name: string;
+  /** null when the asset can never be picked */
+  score: number | null;
+  verdict: AssetVerdict;
+  detectedPlatform: string | null;
+  detectedArch: string | null;
+  selected: boolean;
+}
+
+export interface AssetPickExplanation {
+  repo: string;
+  tag: string;
+  platform: string;
+  arch: string;
+  libc: 'glibc' | 'musl' | 'unknown' | null;
+  /** Number of user rules and exclusions in effect */
+  userRuleCount: number;
+  selected: string | null;
+  assets: AssetExplanation[];
+}
+
+/** User-defined release asset rule (`general.asset_match_rules`) */
+export interface AssetMatchRule {
+  platform?: 'windows' | 'macos' | 'linux' | null;
+  arch?: 'x86_64' | 'aarch64' | 'x86' | null;
+  /** Case-insensitive regex matched against the asset file name */
+  pattern: string;
 }
 
 export interface GitHubWorkflowArtifactInfo {
@@ -50,6 +98,8 @@ export interface GitHubWorkflowArtifactInfo {
   workflowRunNumber: number | null;
   workflowRunBranch: string | null;
   workflowRunHeadSha: string | null;
+  /** Served from the conditional cache without using rate-limit quota */
+  fromCache?: boolean;
 }
 
 export interface GitHubParsedRepo {
@@ -70,6 +120,24 @@ export interface GitHubRepoInfoResponse {
   archived: boolean;
   disabled: boolean;
   topics: string[];
+  /** Served from the conditional cache without using rate-limit quota */
+  fromCache?: boolean;
+}
+
+/** API quota last reported by GitHub or GitLab response headers */
+export interface ApiRateLimitStatus {
+  provider: string;
+  limit: number | null;
+  remaining: number | null;
+  used: number | null;
+  /** Unix time (seconds) at which the window resets */
+  resetAt: number | null;
+  resource: string | null;
+  observedAt: number | null;
+  /** Requests waiting for the window to reset */
+  queued: number;
+  /** Responses answered with 304 and served from the conditional cache */
+  conditionalHits: number;
 }
 
 export type GitHubSourceType = 'release' | 'branch' | 'tag' | 'workflow';
@@ -34,6 +34,8 @@ export interface GitLabAssetInfo {
   url: string;
   directAssetUrl: string | null;
   linkType: string | null;
+  /** Asset the picker would choose for this system */
+  recommended?: boolean;
 }
 
 export interface GitLabSourceInfo {
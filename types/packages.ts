@@ -161,6 +161,8 @@ export interface PackageListProps {
   onUnpin?: (name: string, provider?: string) => void;
   onRollback?: (name: string, version?: string, provider?: string) => void;
   onBookmark?: (name: string, provider?: string) => void;
+  /** Hides actions a package's provider lacks the capability for; defaults to the store's providers */
+  providers?: ProviderInfo[];
   selectable?: boolean;
   showSelectAll?: boolean;
 }
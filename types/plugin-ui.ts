@@ -326,6 +326,40 @@ export interface UiArtifactActionsBlock {
   }[];
 }
 
+// ============================================================================
+// Chart Blocks (schema version 2)
+// ============================================================================
+
+export interface UiChartSeries {
+  name: string;
+  values: number[];
+  color?: string;
+}
+
+export interface UiLineChartBlock {
+  type: 'line-chart';
+  series: UiChartSeries[];
+  /** X-axis labels, one per point */
+  labels?: string[];
+  yAxis?: { min?: number; max?: number; unit?: string };
+  /** Points kept per series, most recent last */
+  maxPoints?: number;
+  title?: string;
+}
+
+export interface UiSparklineBlock {
+  type: 'sparkline';
+  values: number[];
+}
+
+export interface UiStatBlock {
+  type: 'stat';
+  label: string;
+  value: string | number;
+  /** Change since the previous reading */
+  delta?: number;
+}
+
 export type UiLogStreamEntry = UiLogStreamBlock['entries'][number];
 export type UiArtifactAction = UiArtifactActionsBlock['artifacts'][number];
 
@@ -384,6 +418,9 @@ export type UiBlock =
   | UiStepperBlock
   | UiLogStreamBlock
   | UiArtifactActionsBlock
+  | UiLineChartBlock
+  | UiSparklineBlock
+  | UiStatBlock
   | UiGroupBlock;
 
 // ============================================================================
@@ -391,6 +428,8 @@ export type UiBlock =
 // ============================================================================
 
 export interface PluginUiResponse {
+  /** Block schema the plugin built against; absent on version 1 payloads */
+  schemaVersion?: number;
   ui?: UiBlock[];
   state?: Record<string, unknown>;
   outputChannels?: PluginUiOutputChannels;
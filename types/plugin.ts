@@ -109,6 +109,35 @@ export interface PluginPermissionState {
 
 export type PluginPermissionMode = 'compat' | 'strict';
 
+// ============================================================================
+// Plugin Storage
+// ============================================================================
+
+export interface PluginStorageUsage {
+  usageBytes: number;
+  quotaBytes: number;
+  keyCount: number;
+}
+
+export interface PluginDataDirInfo {
+  path: string;
+  storage: PluginStorageUsage;
+}
+
+export interface PluginStorageEntry {
+  key: string;
+  sizeBytes: number;
+  modifiedAt: string | null;
+  /** Leading text of the value; null when the value is binary */
+  preview: string | null;
+}
+
+export interface PluginStorageInspection {
+  pluginId: string;
+  usage: PluginStorageUsage;
+  entries: PluginStorageEntry[];
+}
+
 // ============================================================================
 // Plugin Info (from registry)
 // ============================================================================
@@ -152,6 +181,48 @@ export interface PluginUpdateInfo {
   latestVersion: string;
   downloadUrl: string;
   changelog: string | null;
+  /** Permissions the new version declares that the installed one does not */
+  newPermissions?: string[];
+}
+
+export interface MarketplaceIndexSummary {
+  url: string;
+  pluginCount: number;
+  signed: boolean;
+  keyId: string | null;
+  generatedAt: string | null;
+  fetchedAt: string;
+}
+
+export interface MarketplaceFilter {
+  query?: string | null;
+  tag?: string | null;
+  excludePermission?: string | null;
+}
+
+export interface MarketplacePluginListing {
+  id: string;
+  name: string;
+  version: string;
+  description: string | null;
+  author: string | null;
+  permissions: string[];
+  downloadUrl: string;
+  sha256: string;
+  changelog: string | null;
+  homepage: string | null;
+  tags: string[];
+  installedVersion: string | null;
+  updateAvailable: boolean;
+  signed: boolean;
+}
+
+export interface MarketplaceInstallReport {
+  pluginId: string;
+  version: string;
+  previousVersion: string | null;
+  /** Newly added permissions that stay revoked until explicitly granted */
+  pendingPermissions: string[];
 }
 
 export type PluginMarketplaceActionType = "install" | "update";
@@ -359,6 +430,7 @@ export interface PluginUiEntry {
 export type PluginLanguage = 'rust' | 'javascript' | 'typescript';
 export type ScaffoldLifecycleProfile = 'external' | 'builtin';
 
+export type ScaffoldTemplate = 'basic' | 'ui-panel';
 export type ScaffoldContractTemplate = 'minimal' | 'advanced';
 export type ScaffoldSchemaPreset = 'basic-form' | 'multi-step-flow' | 'repeatable-collection';
 
@@ -404,6 +476,9 @@ export interface ScaffoldConfig {
   additionalKeywords?: string[];
   extensionPoints?: string[];
   templateOptions?: ScaffoldTemplateOptions;
+  template?: ScaffoldTemplate;
+  locales?: string[];
+  withTests?: boolean;
 }
 
 export interface ScaffoldHandoff {
@@ -440,3 +515,22 @@ export interface ValidationResult {
   errors: string[];
   warnings: string[];
 }
+
+/** Active development hot-reload watch */
+export interface PluginDevWatchInfo {
+  pluginId: string;
+  wasmPath: string;
+  manifestPath: string | null;
+  startedAt: string;
+}
+
+/** Payload of the `plugin-reloaded` event */
+export interface PluginReloadedEvent {
+  pluginId: string;
+  wasmPath: string;
+  /** Modification time of the wasm that was loaded */
+  buildTimestamp: string | null;
+  reloadedAt: string;
+  success: boolean;
+  error: string | null;
+}
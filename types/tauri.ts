@@ -61,6 +61,78 @@ export interface EnvInstallProgressEvent {
   totalSize?: number;
   speed?: number;
   error?: string;
+  /** Post-install smoke tests, set on the final completed event */
+  smokeTest?: SmokeTestReport | null;
+}
+
+export interface SmokeTestResult {
+  name: string;
+  command: string;
+  builtin: boolean;
+  passed: boolean;
+  /** Not run: the custom command is neither allowlisted nor approved */
+  skipped: boolean;
+  exitCode: number | null;
+  durationMs: number;
+  message: string | null;
+}
+
+export interface SmokeTestReport {
+  envType: string;
+  version: string;
+  passed: boolean;
+  results: SmokeTestResult[];
+  ranAt: string;
+}
+
+/** User-defined smoke test (`general.smoke_tests`) */
+export interface SmokeTestDefinition {
+  envType: string;
+  name?: string | null;
+  command: string;
+  args?: string[];
+  expectedOutput?: string | null;
+  expectedExitCode?: number | null;
+  /** Set by `envSmokeTestApprove`; only honored while the command line matches */
+  approvedCommand?: string | null;
+}
+
+export interface EnvInstallBatchRequest {
+  envType: string;
+  version: string;
+  providerId?: string | null;
+}
+
+export type EnvBatchItemState =
+  | "queued"
+  | "running"
+  | "completed"
+  | "failed"
+  | "cancelled";
+
+export interface EnvInstallBatchProgress {
+  batchId: string;
+  /** `envType:version`, the same key `envInstallCancel` uses */
+  key: string;
+  state: EnvBatchItemState;
+  progress: number;
+  overallProgress: number;
+  message: string | null;
+}
+
+export interface EnvInstallBatchItem {
+  key: string;
+  request: EnvInstallBatchRequest;
+  state: EnvBatchItemState;
+  error: string | null;
+}
+
+export interface EnvInstallBatchResult {
+  batchId: string;
+  items: EnvInstallBatchItem[];
+  /** Failed requests, ready to pass back to `envInstallBatch` */
+  retryRequests: EnvInstallBatchRequest[];
+  shimError: string | null;
 }
 
 export interface EnvironmentInfo {
@@ -73,6 +145,8 @@ export interface EnvironmentInfo {
   total_size: number;
   version_count: number;
   compiler_metadata?: CppCompilerMetadata | null;
+  /** Versions adopted in place from nvm, pyenv, sdkman or asdf */
+  adopted_versions?: string[];
 }
 
 export interface InstalledVersion {
@@ -83,12 +157,23 @@ export interface InstalledVersion {
   is_current: boolean;
 }
 
+export interface EnvInstalledVersion extends InstalledVersion {
+  /** The latest post-install smoke tests of this version failed */
+  unverified: boolean;
+}
+
 export interface DetectedEnvironment {
   env_type: string;
   version: string;
   source: string;
   source_path: string | null;
   source_type?: 'local' | 'manifest' | 'global' | 'unknown';
+  /** Version manager whose shims resolve the runtime on PATH */
+  resolved_via?: string;
+  /** SDK that actually runs when the pin rolls forward (global.json) */
+  resolved_version?: string;
+  /** Newest available version matching a constraint pin nothing installed satisfies */
+  suggested_version?: string;
 }
 
 export interface EnvVersionMutationResult {
@@ -96,10 +181,75 @@ export interface EnvVersionMutationResult {
   operation: string;
   requestedVersion: string;
   effectiveVersion: string | null;
-  sourceType: 'local' | 'manifest' | 'global' | 'unknown';
+  sourceType: 'local' | 'manifest' | 'global' | 'adopted' | 'unknown';
   success: boolean;
-  status: 'verified' | 'verification_failed';
+  status: 'verified' | 'verification_failed' | 'failed';
   message: string | null;
+  /** `.tool-versions` / `mise.toml` written by `set_local`, if not the provider's own file */
+  pinFile?: string | null;
+  /** Pins from other files that take precedence over the new one */
+  warnings?: string[];
+}
+
+/**
+ * File `envUseLocal` writes pins to. A format passed for a project is
+ * remembered for it; otherwise `general.pin_file_format` applies.
+ */
+export type PinFileFormat = 'native' | 'tool_versions' | 'mise';
+
+export type ForeignManager = 'nvm' | 'pyenv' | 'sdkman' | 'asdf';
+
+export type VersionImportMode = 'preview' | 'adopt' | 'migrate';
+
+export interface ForeignInstall {
+  manager: ForeignManager;
+  envType: string;
+  version: string;
+  installPath: string;
+  binDir: string;
+  adopted: boolean;
+}
+
+export interface ForeignManagerInventory {
+  manager: ForeignManager;
+  root: string | null;
+  detected: boolean;
+  installCount: number;
+}
+
+export interface ForeignEnvInventory {
+  envType: string;
+  installs: ForeignInstall[];
+}
+
+export interface VersionImportInventory {
+  managers: ForeignManagerInventory[];
+  envTypes: ForeignEnvInventory[];
+}
+
+export interface VersionImportSelection {
+  manager: ForeignManager;
+  envType: string;
+  version: string;
+}
+
+export interface VersionImportOutcome {
+  manager: ForeignManager;
+  envType: string;
+  version: string;
+  action: 'adopted' | 'migrated' | 'skipped' | 'failed';
+  success: boolean;
+  providerId: string | null;
+  originalsRemoved: boolean;
+  error: string | null;
+}
+
+export interface VersionImportReport {
+  mode: VersionImportMode;
+  inventory: VersionImportInventory;
+  outcomes: VersionImportOutcome[];
+  succeeded: number;
+  failed: number;
 }
 
 export interface CppCompilerMetadata {
@@ -168,6 +318,72 @@ export interface RustupShowInfo {
   rustcVersion: string | null;
 }
 
+export type RustComponentKind = 'rustc' | 'std' | 'docs' | 'other';
+
+export interface RustComponentSize {
+  name: string;
+  kind: RustComponentKind;
+  /** Target triple of a `rust-std` component */
+  target: string | null;
+  sizeBytes: number;
+}
+
+export interface RustToolchainSize {
+  name: string;
+  /** Toolchain name without the host triple, e.g. `nightly-2024-01-15` */
+  channel: string;
+  path: string;
+  sizeBytes: number;
+  isNightly: boolean;
+  date: string | null;
+  docsBytes: number;
+  components: RustComponentSize[];
+}
+
+export interface RustToolchainSizeReport {
+  rustupHome: string;
+  toolchains: RustToolchainSize[];
+  downloadsBytes: number;
+  totalBytes: number;
+  reclaimableBytes: number;
+}
+
+export interface RustupGcOptions {
+  removeDocs?: string[];
+  nightlyOlderThanDays?: number | null;
+  pruneDownloads?: boolean;
+  projectDirs?: string[];
+  dryRun?: boolean;
+}
+
+export type RustupGcActionKind =
+  | 'remove_docs'
+  | 'uninstall_toolchain'
+  | 'prune_downloads';
+
+export interface RustupGcAction {
+  kind: RustupGcActionKind;
+  toolchain: string | null;
+  estimatedBytes: number;
+  reason: string;
+  applied: boolean;
+  error: string | null;
+}
+
+export interface RustupGcSkipped {
+  toolchain: string;
+  reason: string;
+}
+
+export interface RustupGcResult {
+  dryRun: boolean;
+  actions: RustupGcAction[];
+  skipped: RustupGcSkipped[];
+  estimatedSavings: number;
+  freedBytes: number;
+  scannedProjects: string[];
+}
+
 /** Rustup directory override entry from `rustup override list` */
 export interface RustupOverride {
   path: string;
@@ -231,6 +447,24 @@ export interface GoEnvInfo {
   cgoEnabled: string;
 }
 
+/** Module listed by a `use` directive in go.work */
+export interface GoWorkModule {
+  path: string;
+  dir: string;
+  modulePath: string | null;
+  goVersion: string | null;
+  hasGoMod: boolean;
+}
+
+/** go.work workspace governing a directory */
+export interface GoWorkInfo {
+  goWorkPath: string;
+  root: string;
+  goVersion: string | null;
+  toolchain: string | null;
+  modules: GoWorkModule[];
+}
+
 /** Go cache size info */
 export interface GoCacheInfo {
   buildCachePath: string;
@@ -241,12 +475,20 @@ export interface GoCacheInfo {
   modCacheSizeHuman: string;
 }
 
+/** .NET runtime from `dotnet --list-runtimes` */
+export interface DotnetRuntime {
+  name: string;
+  version: string;
+  installPath: string;
+}
+
 /** Result of verifying an environment installation */
 export interface EnvVerifyResult {
   installed: boolean;
   providerAvailable: boolean;
   currentVersion: string | null;
   requestedVersion: string;
+  smokeTest: SmokeTestReport | null;
 }
 
 // ============================================================================
@@ -293,6 +535,46 @@ export interface PackageInfo {
   provider: string;
 }
 
+/** Search/info response with cache freshness metadata */
+export interface CachedResponse<T> {
+  data: T;
+  /** Data is past its TTL (provider unreachable or offline mode) */
+  stale: boolean;
+  fromCache: boolean;
+  cachedAt: string | null;
+  offline: boolean;
+  /** Error from the failed provider request when stale data was returned */
+  error: string | null;
+}
+
+export interface CacheWarmFailure {
+  query: string;
+  error: string;
+}
+
+export interface CacheWarmResult {
+  provider: string;
+  warmed: string[];
+  failed: CacheWarmFailure[];
+}
+
+export type SelftestFindingSeverity = 'error' | 'warning';
+
+export interface SelftestFinding {
+  severity: SelftestFindingSeverity;
+  path: string;
+  message: string;
+}
+
+export interface ProviderSelftestReport {
+  provider: string;
+  endpoint: string;
+  compatible: boolean;
+  durationMs: number;
+  findings: SelftestFinding[];
+  error: string | null;
+}
+
 export interface VersionInfo {
   version: string;
   release_date: string | null;
@@ -300,6 +582,31 @@ export interface VersionInfo {
   yanked: boolean;
 }
 
+export interface AvailableVersions {
+  versions: VersionInfo[];
+  /** When the list was last synced from upstream (RFC 3339) */
+  lastSynced: string | null;
+}
+
+export type VersionSyncMode = 'unchanged' | 'full' | 'delta';
+
+export interface VersionSyncStats {
+  mode: VersionSyncMode;
+  added: number;
+  /** Versions newly marked yanked because upstream stopped listing them */
+  yanked: number;
+  /** Yanked versions dropped after the retention period */
+  pruned: number;
+}
+
+export interface VersionSyncResult {
+  envType: string;
+  providerId: string;
+  versions: VersionInfo[];
+  lastSynced: string;
+  stats: VersionSyncStats;
+}
+
 export interface InstalledPackage {
   name: string;
   version: string;
@@ -307,6 +614,8 @@ export interface InstalledPackage {
   install_path: string;
   installed_at: string;
   is_global: boolean;
+  /** Provider-specific kind, e.g. `formula` / `cask` for brew */
+  kind?: string;
 }
 
 export type PackageValidationStatus = 'pass' | 'warning' | 'failure';
@@ -332,6 +641,25 @@ export interface PackagePreflightSummary {
   checked_at: string;
 }
 
+/** A package installed into a throwaway prefix by `package_try` */
+export interface PackageTryInfo {
+  id: string;
+  provider: string;
+  name: string;
+  version: string;
+  prefix: string;
+  /** Directories to put on PATH to use the package */
+  binDirs: string[];
+  /** Executables the package brought into `binDirs` */
+  binPaths: string[];
+  envVars: Record<string, string>;
+  createdAt: string;
+  expiresAt: string;
+  /** Shell commands for the user's shell that activate the prefix */
+  activationScript: string;
+  expired: boolean;
+}
+
 // ============================================================================
 // Provider Types
 // ============================================================================
@@ -351,6 +679,8 @@ export interface ProviderStatusInfo {
   display_name: string;
   installed: boolean;
   platforms: string[];
+  /** Operations the provider declares support for */
+  capabilities?: string[];
   scope_state?: 'available' | 'unavailable' | 'timeout' | 'unsupported';
   scope_reason?: string | null;
   status?: 'supported' | 'unsupported';
@@ -359,6 +689,17 @@ export interface ProviderStatusInfo {
   update_supported?: boolean;
   update_reason?: string | null;
   update_reason_code?: string | null;
+  /** Manager-specific figures, reported only for available providers */
+  health?: ProviderHealthMetric[];
+  /** Operations that need admin rights, for system package managers */
+  elevated_operations?: string[];
+}
+
+/** A manager-specific health figure such as bucket or outdated counts */
+export interface ProviderHealthMetric {
+  key: string;
+  label: string;
+  value: number;
 }
 
 // ============================================================================
@@ -410,6 +751,8 @@ export interface CacheEntryItem {
   created_at: string;
   last_accessed: string | null;
   hit_count: number;
+  /** Installed environments (`env@version`) still using this entry */
+  referenced_by?: string[];
 }
 
 export interface CacheEntryList {
@@ -444,6 +787,41 @@ export interface CacheVerificationResult {
   size_mismatches: number;
   is_healthy: boolean;
   details: CacheIssue[];
+  /** Corrupted download files moved into the quarantine directory */
+  quarantined_entries: number;
+  /** Download files skipped because they are unchanged since they last verified clean */
+  skipped_unchanged: number;
+  bytes_hashed: number;
+}
+
+/** Payload of the `cache-verify-progress` event */
+export interface CacheVerifyProgress {
+  entriesDone: number;
+  entriesTotal: number;
+  /** Unchanged files skipped since an earlier run verified them */
+  entriesSkipped: number;
+  bytesHashed: number;
+  bytesTotal: number;
+}
+
+/** A corrupted cache file moved out of the download cache */
+export interface CacheQuarantineEntry {
+  id: string;
+  original_path: string;
+  quarantined_path: string;
+  entry_keys: string[];
+  expected_checksum: string;
+  /** null when the file was quarantined for its size alone */
+  actual_checksum: string | null;
+  size: number;
+  reason: 'checksum_mismatch' | 'size_mismatch';
+  quarantined_at: string;
+}
+
+export interface CacheQuarantinePurgeResult {
+  purged_count: number;
+  freed_bytes: number;
+  freed_human: string;
 }
 
 export interface CacheIssue {
@@ -474,6 +852,7 @@ export interface CacheSettings {
   auto_clean_threshold?: number;
   monitor_interval?: number;
   monitor_external?: boolean;
+  monthly_summary?: boolean;
   external_cache_excluded_providers?: string[];
   custom_cache_entries?: CustomCacheEntry[];
 }
@@ -484,6 +863,8 @@ export interface CleanPreviewItem {
   size_human: string;
   entry_type: string;
   created_at: string;
+  /** Installed environments (`env@version`) still using this download */
+  referenced_by?: string[];
 }
 
 export interface CleanPreview {
@@ -491,6 +872,12 @@ export interface CleanPreview {
   total_count: number;
   total_size: number;
   total_size_human: string;
+  /** Bytes cleanup will actually free */
+  safe_size?: number;
+  safe_size_human?: string;
+  /** Bytes kept because installed environments reference them */
+  referenced_size?: number;
+  referenced_size_human?: string;
   skipped?: DefaultDownloadsSkipItem[];
   skipped_count?: number;
 }
@@ -522,6 +909,7 @@ export interface CleanupRecord {
   file_count: number;
   files: CleanedFileInfo[];
   files_truncated: boolean;
+  mode?: string;
 }
 
 export interface CleanupHistorySummary {
@@ -550,6 +938,60 @@ export interface CacheSizeSnapshot {
   metadataCount: number;
 }
 
+export type CleanupAnalyticsPeriod = "month" | "quarter" | "year" | "all";
+
+export type CleanupCategory =
+  | "expired_downloads"
+  | "metadata"
+  | "external_caches"
+  | "framework_caches"
+  | "other";
+
+export interface WeeklyCleanup {
+  /** Monday of the week, as `YYYY-MM-DD` */
+  weekStart: string;
+  cleanups: number;
+  freedBytes: number;
+  freedHuman: string;
+}
+
+export interface CategoryCleanup {
+  category: CleanupCategory;
+  freedBytes: number;
+  freedHuman: string;
+  fileCount: number;
+}
+
+export interface DirectoryCleanup {
+  path: string;
+  freedBytes: number;
+  freedHuman: string;
+  fileCount: number;
+  cleanups: number;
+}
+
+export interface CacheGrowthProjection {
+  sampleCount: number;
+  internalBytesPerDay: number;
+  totalBytesPerDay: number | null;
+  currentSize: number;
+  maxSize: number;
+  /** Days until the internal cache reaches `maxSize` at the current rate */
+  daysUntilMax: number | null;
+}
+
+export interface CleanupAnalytics {
+  period: CleanupAnalyticsPeriod;
+  generatedAt: string;
+  totalCleanups: number;
+  totalFreedBytes: number;
+  totalFreedHuman: string;
+  weekly: WeeklyCleanup[];
+  categories: CategoryCleanup[];
+  topDirectories: DirectoryCleanup[];
+  growth: CacheGrowthProjection | null;
+}
+
 export interface CacheAutoCleanedEvent {
   expiredMetadataRemoved: number;
   expiredDownloadsFreed: number;
@@ -683,6 +1125,105 @@ export interface BackupCleanupResult {
   error: string | null;
 }
 
+export type StateComponent =
+  | 'config'
+  | 'terminal_profiles'
+  | 'custom_rules'
+  | 'workspaces'
+  | 'state'
+  | 'shims'
+  | 'plugins'
+  | 'plugin_data'
+  | 'download_cache';
+
+export interface FullBackupOptions {
+  /** Defaults to `<root>/backups/cognia-full-<timestamp>.zip` */
+  destPath?: string | null;
+  includeDownloadCache?: boolean;
+  note?: string | null;
+}
+
+export interface FullBackupComponentEntry {
+  component: StateComponent;
+  schemaVersion: number;
+  fileCount: number;
+  totalSize: number;
+  /** Path relative to the component -> sha256 */
+  files: Record<string, string>;
+}
+
+export interface FullBackupManifest {
+  formatVersion: number;
+  appVersion: string;
+  createdAt: string;
+  platform: string;
+  hostname: string;
+  note: string | null;
+  components: FullBackupComponentEntry[];
+}
+
+export type FullBackupPhase =
+  | 'scanning'
+  | 'archiving'
+  | 'staging'
+  | 'migrating'
+  | 'swapping'
+  | 'done';
+
+export interface FullBackupProgress {
+  phase: FullBackupPhase;
+  component: StateComponent | null;
+  filesDone: number;
+  totalFiles: number;
+  bytesDone: number;
+  totalBytes: number;
+}
+
+export interface FullBackupResult {
+  success: boolean;
+  status: BackupOperationStatus;
+  reasonCode?: string | null;
+  issues?: BackupOperationIssue[];
+  path: string;
+  manifest: FullBackupManifest | null;
+  size: number;
+  durationMs: number;
+  error: string | null;
+}
+
+export interface FullBackupComponentStatus {
+  component: StateComponent;
+  schemaVersion: number;
+  supportedSchemaVersion: number;
+  fileCount: number;
+  totalSize: number;
+  /** False when written by a newer release */
+  compatible: boolean;
+  needsMigration: boolean;
+  requiresRestart: boolean;
+}
+
+export interface FullBackupInspection {
+  valid: boolean;
+  path: string;
+  size: number;
+  issues?: BackupOperationIssue[];
+  manifest: FullBackupManifest | null;
+  components: FullBackupComponentStatus[];
+}
+
+export interface FullRestoreResult {
+  success: boolean;
+  status: BackupOperationStatus;
+  reasonCode?: string | null;
+  issues?: BackupOperationIssue[];
+  restored: StateComponent[];
+  configMigratedFrom: number | null;
+  restartRequired: boolean;
+  durationMs: number;
+  error: string | null;
+}
+
 export interface IntegrityCheckResult {
   ok: boolean;
   errors: string[];
@@ -710,6 +1251,63 @@ export interface GpuInfo {
   vendor: string | null;
 }
 
+export interface SettingsValidationIssue {
+  /** Dotted config key, empty when the whole file was unreadable */
+  key: string;
+  message: string;
+}
+
+export interface SettingsLoadReport {
+  fromVersion: number;
+  toVersion: number;
+  issues: SettingsValidationIssue[];
+  backupPath: string | null;
+}
+
+/** One key an imported config would change */
+export interface ConfigChange {
+  key: string;
+  kind: 'added' | 'changed' | 'removed';
+  oldValue: string | null;
+  newValue: string | null;
+  /** Values are masked because they look like credentials */
+  secret: boolean;
+  /** Applying it rebuilds providers or the download manager */
+  requiresReinit: boolean;
+}
+
+export interface ConfigImportPreview {
+  changes: ConfigChange[];
+  report: SettingsLoadReport;
+}
+
+/** Configuration section a team config bundle can carry */
+export type TeamConfigSection =
+  | 'mirrors'
+  | 'proxy'
+  | 'providers'
+  | 'detection_rules';
+
+/** What applying a team config bundle changes; rule entries are keyed `detection_rules.<id>` */
+export interface TeamConfigPreview {
+  version: number;
+  createdAt: string;
+  sections: TeamConfigSection[];
+  changes: ConfigChange[];
+  report: SettingsLoadReport;
+}
+
+/** Drift of the local configuration from the last applied team config bundle */
+export interface TeamConfigStatus {
+  applied: boolean;
+  version: number | null;
+  createdAt: string | null;
+  appliedAt: string | null;
+  bundleHash: string | null;
+  drifted: boolean;
+  driftedSections: TeamConfigSection[];
+}
+
 export interface PlatformInfo {
   os: string;
   arch: string;
@@ -765,10 +1363,18 @@ export interface SystemProxyInfo {
   source: 'environment' | 'windows_registry' | 'none';
 }
 
+export type ProxySource = 'provider' | 'global' | 'none';
+
+export interface EffectiveProxy {
+  proxyUrl: string | null;
+  source: ProxySource;
+}
+
 export interface ProxyTestResult {
   success: boolean;
   latencyMs: number;
   error: string | null;
+  effectiveProxy: EffectiveProxy | null;
 }
 
 export interface NetworkInterfaceInfo {
@@ -826,12 +1432,18 @@ export type BatchProgress =
   | { type: 'downloading'; package: string; progress: number; current: number; total: number }
   | { type: 'installing'; package: string; current: number; total: number }
   | { type: 'item_completed'; package: string; success: boolean; current: number; total: number }
+  | { type: 'verifying'; package: string; current: number; total: number }
+  | { type: 'rolling_back'; package: string; to_version: string; current: number; total: number }
   | { type: 'completed'; result: BatchResult };
 
 export interface BatchResult {
   successful: BatchItemResult[];
   failed: BatchItemError[];
   skipped: BatchItemSkipped[];
+  /** Updates that failed verification and were restored */
+  rolled_back?: BatchItemRollback[];
+  /** Packages skipped because they are pinned */
+  pinned?: BatchItemSkipped[];
   total_time_ms: number;
 }
 
@@ -854,6 +1466,16 @@ export interface BatchItemSkipped {
   reason: string;
 }
 
+export interface BatchItemRollback {
+  name: string;
+  provider: string;
+  from_version: string;
+  to_version: string;
+  reason: string;
+  /** Pinned to `to_version` to prevent re-update */
+  pinned: boolean;
+}
+
 // ============================================================================
 // Update Types
 // ============================================================================
@@ -912,6 +1534,9 @@ export interface SelfUpdateInfo {
   current_version: string;
   latest_version: string | null;
   update_available: boolean;
+  /** The channel's latest build is older; installing needs `allowDowngrade` */
+  downgrade_available?: boolean;
+  channel?: UpdateChannel;
   release_notes: string | null;
   selected_source?: SelfUpdateSourceKind | null;
   attempted_sources?: SelfUpdateSourceKind[];
@@ -921,18 +1546,67 @@ export interface SelfUpdateInfo {
 
 export type SelfUpdateSourceKind = 'official' | 'mirror' | 'custom';
 
-export type SelfUpdateErrorCategory =
-  | 'source_unavailable'
-  | 'network'
-  | 'timeout'
-  | 'validation'
-  | 'signature'
-  | 'no_update'
-  | 'unknown';
+export type UpdateChannel = 'stable' | 'beta' | 'nightly';
 
-export interface SelfUpdateProgressEvent {
-  progress: number | null;
-  status: 'downloading' | 'installing' | 'done' | 'error';
+/** Latest build of a channel, i.e. what switching to it would install */
+export interface SelfChannelVersion {
+  channel: UpdateChannel;
+  current_version: string;
+  version: string | null;
+  release_notes: string | null;
+  is_current: boolean;
+  is_downgrade: boolean;
+  selected_source: SelfUpdateSourceKind | null;
+  error_category: SelfUpdateErrorCategory | null;
+  error_message: string | null;
+}
+
+export type ScheduleInterval = 'daily' | 'weekly';
+
+/** OS-level scheduled background update check */
+export interface ScheduleStatus {
+  /** Scheduler backend for this platform */
+  backend: 'launchd' | 'schtasks' | 'systemd';
+  /** Whether the OS currently has the task registered */
+  registered: boolean;
+  interval: ScheduleInterval | null;
+  executable: string | null;
+  /** False when the registered executable no longer matches the running binary */
+  executableCurrent: boolean;
+  registeredAt: string | null;
+}
+
+/** Result of the last headless scheduled update check */
+export interface BackgroundUpdateReport {
+  checkedAt: string;
+  environments: EnvUpdateCheckResult[];
+  outdatedEnvironments: number;
+  selfUpdate: SelfUpdateInfo | null;
+  eolWarnings: BackgroundEolWarning[];
+  errors: string[];
+}
+
+export interface BackgroundEolWarning {
+  envType: string;
+  version: string;
+  cycle: string;
+  eol: string | null;
+  isEol: boolean;
+}
+
+export type SelfUpdateErrorCategory =
+  | 'source_unavailable'
+  | 'network'
+  | 'timeout'
+  | 'validation'
+  | 'signature'
+  | 'no_update'
+  | 'downgrade_requires_confirmation'
+  | 'unknown';
+
+export interface SelfUpdateProgressEvent {
+  progress: number | null;
+  status: 'downloading' | 'installing' | 'done' | 'error';
   selectedSource?: SelfUpdateSourceKind;
   attemptedSources?: SelfUpdateSourceKind[];
   errorCategory?: SelfUpdateErrorCategory;
@@ -953,6 +1627,42 @@ export interface EnvUpdateCheckResult {
   isOutdated: boolean;
 }
 
+export type ReleaseNoteSource =
+  | 'github'
+  | 'nodejs'
+  | 'python'
+  | 'npm'
+  | 'crates'
+  | 'pypi'
+  | 'link';
+
+/** Notes for one version in an update range */
+export interface ReleaseNoteEntry {
+  version: string;
+  date: string | null;
+  url: string | null;
+  /** Markdown headers of the notes */
+  highlights: string[];
+  /** Flagged as a security release or mentions CVEs */
+  security: boolean;
+  /** Release notes markdown, cut to a readable length */
+  body: string | null;
+  source: ReleaseNoteSource;
+}
+
+/** Result of `release_notes_get` */
+export interface ReleaseNotes {
+  fromVersion: string;
+  toVersion: string;
+  /** Newest first */
+  entries: ReleaseNoteEntry[];
+  /** No entry has notes text, only links */
+  degraded: boolean;
+  hasSecurity: boolean;
+  /** More versions were in range than listed */
+  truncated: boolean;
+}
+
 export interface EnvCleanupResult {
   removed: CleanedVersion[];
   freedBytes: number;
@@ -964,9 +1674,45 @@ export interface CleanedVersion {
   size: number;
 }
 
+export type InstallProblem =
+  | { kind: 'missing_directory' }
+  | { kind: 'empty_directory' }
+  | { kind: 'missing_binary'; binary: string }
+  | { kind: 'not_executable'; path: string }
+  | { kind: 'binary_failed'; binary: string; message: string }
+  | { kind: 'version_mismatch'; expected: string; reported: string }
+  | { kind: 'support_tool_broken'; tool: string; message: string };
+
+export interface InstallValidation {
+  envType: string;
+  version: string;
+  installPath: string;
+  /** Empty when the install is healthy */
+  problems: InstallProblem[];
+  /** The problems prove the install is unusable; doubtful findings are report-only */
+  removable: boolean;
+}
+
+export type NodePackageManager = 'npm' | 'pnpm' | 'yarn' | 'bun';
+
 export interface GlobalPackageInfo {
   name: string;
   version: string;
+  /** Executables the package provides, when known */
+  bins: string[];
+  /** Node package manager that installed the package */
+  packageManager?: NodePackageManager;
+}
+
+export interface NodePackageManagers {
+  available: NodePackageManager[];
+  preferred: NodePackageManager | null;
+}
+
+export interface EnvGlobalUpdateResult {
+  updated: string[];
+  failed: MigrateFailure[];
+  skipped: string[];
 }
 
 export interface EnvMigrateResult {
@@ -991,6 +1737,99 @@ export interface EolCycleInfo {
   eolApproaching: boolean;
 }
 
+/** A project directory pinning an environment version (e.g. via .nvmrc) */
+export interface EnvProjectPin {
+  envType: string;
+  version: string;
+  projectDir: string;
+  source: string;
+}
+
+export type EolSupportStatus = 'supported' | 'approaching' | 'eol' | 'unknown';
+
+export interface EolUpgradeTarget {
+  version: string;
+  cycle: string;
+  isLts: boolean;
+  reason: 'patch' | 'same_major' | 'next_lts' | 'next_supported';
+  eol: string | null;
+}
+
+export interface EolVersionAdvice {
+  version: string;
+  isCurrent: boolean;
+  cycle: string | null;
+  status: EolSupportStatus;
+  eol: string | null;
+  /** Negative once the EOL date has passed */
+  daysUntilEol: number | null;
+  recommended: EolUpgradeTarget | null;
+  pinnedBy: EnvProjectPin[];
+}
+
+/** EOL-aware upgrade advice for one environment type */
+export interface EnvUpgradeAdvice {
+  envType: string;
+  providerId: string;
+  warningDays: number;
+  /** 0-100; null when no installed version matched EOL data */
+  score: number | null;
+  supportedCount: number;
+  approachingCount: number;
+  eolCount: number;
+  unknownCount: number;
+  versions: EolVersionAdvice[];
+  scannedProjects: string[];
+  eolAvailable: boolean;
+  /** EOL data came from an expired cache because the API was unreachable */
+  eolStale: boolean;
+  eolError: string | null;
+  checkedAt: string;
+}
+
+export type ResolutionStepKind =
+  | 'project_pin'
+  | 'global_version'
+  | 'cognia_activation'
+  | 'path_entry'
+  | 'cognia_shim'
+  | 'version_manager_init';
+
+export interface ResolutionStep {
+  kind: ResolutionStepKind;
+  label: string;
+  path: string | null;
+  version: string | null;
+  /** Version manager owning this step (`pyenv`, `nvm`, `cognia`, ...) */
+  manager: string | null;
+  detail: string;
+  winsInShell: boolean;
+  winsInCognia: boolean;
+}
+
+export interface ResolutionVerdict {
+  binaryPath: string | null;
+  version: string | null;
+  /** Indices into `steps` that decided this verdict */
+  steps: number[];
+  explanation: string;
+}
+
+/** Why a given version of an environment is (or isn't) picked up */
+export interface EnvResolutionReport {
+  envType: string;
+  cwd: string;
+  binary: string;
+  steps: ResolutionStep[];
+  /** What a newly opened terminal runs */
+  freshShell: ResolutionVerdict;
+  /** What Cognia-launched processes run */
+  cognia: ResolutionVerdict;
+  /** Both verdicts have a version and they disagree */
+  mismatch: boolean;
+  warnings: string[];
+}
+
 // ============================================================================
 // Install History Types
 // ============================================================================
@@ -1004,6 +1843,8 @@ export interface InstallHistoryEntry {
   provider: string;
   success: boolean;
   error_message: string | null;
+  /** Plugin that performed the action, when it was not the user */
+  actor_plugin_id?: string | null;
 }
 
 export type InstallHistoryAction = 'install' | 'uninstall' | 'update' | 'rollback';
@@ -1142,6 +1983,30 @@ export interface SearchSuggestion {
   provider: string | null;
 }
 
+export type GlobalSearchKind = 'package' | 'environment' | 'download' | 'setting';
+
+export interface GlobalSearchResult {
+  kind: GlobalSearchKind;
+  id: string;
+  title: string;
+  subtitle: string | null;
+  score: number;
+}
+
+export interface GlobalSearchSourceStatus {
+  kind: GlobalSearchKind;
+  status: 'ok' | 'timeout' | 'cancelled' | 'error';
+  error: string | null;
+  count: number;
+}
+
+export interface GlobalSearchResponse {
+  query: string;
+  results: GlobalSearchResult[];
+  sources: GlobalSearchSourceStatus[];
+  cached: boolean;
+}
+
 // ============================================================================
 // Package Comparison Types
 // ============================================================================
@@ -1186,6 +2051,39 @@ export interface ManifestInfo {
   path: string;
 }
 
+export type ManifestFormat = 'toml' | 'yaml';
+
+export type ManifestDiagnosticSeverity = 'error' | 'warning' | 'hint';
+
+/** 1-based lines and columns; the end column is exclusive */
+export interface ManifestTextRange {
+  startLine: number;
+  startColumn: number;
+  endLine: number;
+  endColumn: number;
+}
+
+export interface ManifestDiagnostic {
+  code: string;
+  severity: ManifestDiagnosticSeverity;
+  message: string;
+  path: string | null;
+  range: ManifestTextRange;
+}
+
+export interface ManifestDiagnostics {
+  path: string;
+  format: ManifestFormat;
+  diagnostics: ManifestDiagnostic[];
+  lockfilePath: string | null;
+}
+
+export interface ManifestFormatResult {
+  path: string;
+  changed: boolean;
+  content: string;
+}
+
 // ============================================================================
 // Log Types
 // ============================================================================
@@ -1244,6 +2142,25 @@ export interface LogQueryMeta {
   queryFingerprint: string;
 }
 
+export interface LogStatsOptions {
+  fileName?: string;
+  startTime?: number | null;
+  endTime?: number | null;
+}
+
+export interface LogStats {
+  totalCount: number;
+  byLevel: Record<string, number>;
+  byTarget: Record<string, number>;
+  firstTimestamp: string | null;
+  lastTimestamp: string | null;
+}
+
+export interface LogFollowBatch {
+  followId: string;
+  entries: LogEntry[];
+}
+
 export interface LogExportOptions {
   fileName?: string;
   levelFilter?: string[];
@@ -1329,6 +2246,8 @@ export interface DownloadProgress {
   etaHuman: string | null;
   downloadedHuman: string;
   totalHuman: string | null;
+  /** No data has arrived for the stall window */
+  stalled: boolean;
 }
 
 export type DownloadSourceKind =
@@ -1450,6 +2369,15 @@ export interface DownloadQueueStats {
   overallProgress: number;
 }
 
+/** Archive extraction progress; snake_case like the other download events */
+export interface ExtractProgress {
+  current_file: string;
+  files_done: number;
+  /** Known up front for zip archives only */
+  total_files: number | null;
+  bytes_written: number;
+}
+
 export interface DownloadHistoryRecord {
   id: string;
   url: string;
@@ -1473,6 +2401,49 @@ export interface DownloadHistoryRecord {
   artifactProfile?: DownloadArtifactProfile | null;
 }
 
+export type DownloadHistoryExportFormat = 'csv' | 'json';
+
+export interface DownloadHistoryFilter {
+  status?: DownloadHistoryRecord['status'][];
+  /** RFC 3339 timestamp, inclusive */
+  from?: string | null;
+  /** RFC 3339 timestamp, inclusive */
+  to?: string | null;
+  provider?: string | null;
+}
+
+export interface DownloadHistoryImportResult {
+  imported: number;
+  skipped: number;
+  total: number;
+}
+
+export type DownloadUsagePeriod = 'day' | 'week' | 'month' | 'year' | 'all';
+
+export interface DownloadUsageTotal {
+  /** Provider, day (YYYY-MM-DD) or host, depending on the breakdown */
+  key: string;
+  networkBytes: number;
+  cacheHitBytes: number;
+  downloadCount: number;
+  cacheHitCount: number;
+}
+
+export interface DownloadUsageStats {
+  period: DownloadUsagePeriod;
+  since: string | null;
+  totalNetworkBytes: number;
+  totalNetworkHuman: string;
+  /** Bytes served from the download cache instead of the network */
+  totalCacheHitBytes: number;
+  totalSavedHuman: string;
+  downloadCount: number;
+  cacheHitCount: number;
+  byProvider: DownloadUsageTotal[];
+  byDay: DownloadUsageTotal[];
+  byHost: DownloadUsageTotal[];
+}
+
 export interface DownloadHistoryStats {
   totalCount: number;
   completedCount: number;
@@ -1514,6 +2485,17 @@ export interface DownloadRequest {
   installIntent?: DownloadInstallIntent;
   sourceDescriptor?: DownloadSourceDescriptor;
   artifactProfile?: DownloadArtifactProfile;
+  /** Environment version consuming this artifact; protects its cache entry from eviction */
+  envReference?: DownloadEnvReference;
+  /** Probe `<url>.sha256` when no checksum is given (default false) */
+  discoverChecksum?: boolean;
+  /** Extra task metadata, e.g. `checksum_source` of an attached checksum */
+  metadata?: Record<string, string>;
+}
+
+export interface DownloadEnvReference {
+  envType: string;
+  version: string;
 }
 
 export interface VerifyResult {
@@ -1523,6 +2505,25 @@ export interface VerifyResult {
   error: string | null;
 }
 
+export type PartialDownloadStatus =
+  | 'consistent'
+  | 'missing'
+  | 'truncated'
+  | 'over_length'
+  | 'tail_mismatch'
+  | 'unjournaled';
+
+/** Partial download checked against its resume journal */
+export interface PartialValidation {
+  url: string;
+  filePath: string;
+  status: PartialDownloadStatus;
+  journalSize: number;
+  diskSize: number;
+  verifiedSize: number;
+  repaired: boolean;
+}
+
 export interface DownloadShutdownOutcome {
   paused: number;
   fallbackCancelled: number;
@@ -1544,7 +2545,14 @@ export type DownloadEvent =
   | { type: 'task_paused'; task_id: string }
   | { type: 'task_resumed'; task_id: string }
   | { type: 'task_cancelled'; task_id: string }
-  | { type: 'queue_updated'; stats: DownloadQueueStats };
+  | { type: 'queue_updated'; stats: DownloadQueueStats }
+  | { type: 'queue_restored'; resumed: number; failed: number; pending: number };
+
+export interface DownloadQueueRestored {
+  resumed: number;
+  failed: number;
+  pending: number;
+}
 
 // ============================================================================
 // System Tray Types
@@ -1623,8 +2631,59 @@ export interface TrayStateInfo {
   notificationEvents: TrayNotificationEvent[];
   alwaysOnTop: boolean;
   menuConfig: TrayMenuConfig;
+  unreadNotifications: number;
+}
+
+// ============================================================================
+// Notification Center Types
+// ============================================================================
+
+export type NotificationCategory =
+  | 'download'
+  | 'update'
+  | 'eol'
+  | 'install_failure'
+  | 'system';
+
+export type NotificationSeverity = 'info' | 'success' | 'warning' | 'error';
+
+/** Per-category delivery preference (`notifications.<category>` config key) */
+export type NotificationDelivery = 'toast' | 'in_app' | 'muted';
+
+export interface NotificationRecord {
+  id: string;
+  category: NotificationCategory;
+  severity: NotificationSeverity;
+  title: string;
+  body: string;
+  payload: unknown;
+  createdAt: string;
+  read: boolean;
+}
+
+export interface NotificationFilter {
+  category?: NotificationCategory | null;
+  severity?: NotificationSeverity | null;
+  unreadOnly?: boolean;
+  limit?: number | null;
 }
 
+// ============================================================================
+// Deep Link Types
+// ============================================================================
+
+/** Action described by a `cognia://` link; executed only after confirmation */
+export type DeepLinkAction =
+  | { action: 'env_install'; envType: string; version: string; providerId: string | null }
+  | { action: 'package_install'; provider: string; name: string; version: string | null }
+  | { action: 'plugin_install'; storeId: string }
+  | { action: 'open_settings'; section: string | null };
+
+export type DeepLinkRequest = DeepLinkAction & {
+  /** The link in normalized form */
+  url: string;
+};
+
 // ============================================================================
 // Custom Detection Rules Types
 // ============================================================================
@@ -1639,7 +2698,14 @@ export type ExtractionStrategy =
   | { type: 'plain_text'; strip_prefix?: string; strip_suffix?: string }
   | { type: 'tool_versions'; tool_name: string }
   | { type: 'ini_key'; section?: string; key: string }
-  | { type: 'command'; cmd: string; args: string[]; output_pattern: string };
+  | { type: 'command'; cmd: string; args: string[]; output_pattern: string }
+  | {
+      type: 'command_output';
+      command: string;
+      args?: string[];
+      json_pointer?: string;
+      regex?: string;
+    };
 
 /** Version transformation options */
 export interface VersionTransform {
@@ -1663,6 +2729,8 @@ export interface CustomDetectionRule {
   tags: string[];
   created_at?: string;
   updated_at?: string;
+  /** Command line the user approved; cleared when the command changes */
+  approved_command?: string | null;
 }
 
 /** Result of custom rule detection */
@@ -1675,6 +2743,22 @@ export interface CustomDetectionResult {
   raw_version: string;
 }
 
+/** Rule left out of a detection run because it could not be evaluated */
+export interface CustomDetectionSkipped {
+  rule_id: string;
+  rule_name: string;
+  env_type: string;
+  /** The rule runs a command the user has not approved */
+  needs_approval: boolean;
+  reason: string;
+}
+
+/** Versions found by all custom rules, with the rules that were skipped */
+export interface CustomDetectionReport {
+  results: CustomDetectionResult[];
+  skipped: CustomDetectionSkipped[];
+}
+
 /** Result of testing a rule */
 export interface TestRuleResult {
   success: boolean;
@@ -1708,6 +2792,38 @@ export interface ExtractionTypeInfo {
   example: string;
 }
 
+/** Program and arguments with `{version}` / `{install_dir}` placeholders */
+export interface CommandTemplate {
+  command: string;
+  args: string[];
+}
+
+/** Where a custom environment type gets its installable versions from */
+export type VersionListSource =
+  | { type: 'static'; versions: string[] }
+  | {
+      type: 'github_releases';
+      repo: string;
+      tag_prefix?: string | null;
+      include_prereleases: boolean;
+    }
+  | { type: 'command'; command: string; args: string[]; regex?: string | null };
+
+/** User-defined environment type */
+export interface CustomEnvTypeDefinition {
+  id: string;
+  display_name: string;
+  description?: string | null;
+  detection_rules: string[];
+  version_source: VersionListSource;
+  install: CommandTemplate;
+  uninstall?: CommandTemplate | null;
+  binaries: string[];
+  approved_commands: string[];
+  created_at?: string | null;
+  updated_at?: string | null;
+}
+
 // ============================================================================
 // Health Check Types
 // ============================================================================
@@ -1820,6 +2936,47 @@ export interface HealthRemediationResult {
   stderr: string | null;
 }
 
+// ============================================================================
+// Job Types
+// ============================================================================
+
+export type JobKind =
+  | 'env_install'
+  | 'batch_install'
+  | 'cache_migrate'
+  | 'cache_verify'
+  | 'wsl_export';
+
+export type JobStatus =
+  | 'running'
+  | 'completed'
+  | 'failed'
+  | 'cancelled'
+  | 'interrupted';
+
+/** A long-running operation tracked by the operation queue */
+export interface JobRecord {
+  id: string;
+  kind: JobKind;
+  title: string;
+  params: Record<string, unknown> | null;
+  status: JobStatus;
+  /** 0–100 when the operation reports progress */
+  progress: number | null;
+  message: string | null;
+  error: string | null;
+  startedAt: string;
+  finishedAt: string | null;
+  /** Interrupted job that can be resumed by re-running it with `params` */
+  resumable: boolean;
+}
+
+/** Payload of the `job-updated` event */
+export interface JobEvent {
+  lifecycle: 'started' | 'progress' | 'finished';
+  job: JobRecord;
+}
+
 // ============================================================================
 // Environment Profiles Types
 // ============================================================================
@@ -1884,6 +3041,32 @@ export interface ProfileApplyResult {
   wsl_snapshot?: WslProfileApplyResult | null;
 }
 
+/** Availability of one profile environment on this machine */
+export interface ProfileImportEnvironmentStatus {
+  env_type: string;
+  version: string;
+  provider_id: string;
+  provider_available: boolean;
+  version_installed: boolean;
+  issue: string | null;
+}
+
+/** What importing a profile payload would do, without importing it */
+export interface ProfileImportPreview {
+  profile_name: string;
+  description: string | null;
+  /** `null` for legacy exports that are a bare profile object */
+  format_version: number | null;
+  environments: ProfileImportEnvironmentStatus[];
+  /** Env types whose provider or version is unavailable here */
+  unavailable: string[];
+  /** Redacted env snapshot keys that need values before import */
+  missing_secrets: string[];
+  has_env_snapshot: boolean;
+  has_wsl_snapshot: boolean;
+  warnings: string[];
+}
+
 export interface ProfileEnvironmentResult {
   env_type: string;
   version: string;
@@ -1904,6 +3087,80 @@ export interface ProfileEnvironmentSkipped {
   provider_id?: string | null;
 }
 
+// ============================================================================
+// Workspace Types
+// ============================================================================
+
+export interface RepoFingerprint {
+  head: string | null;
+  manifestMtimes: Record<string, number>;
+}
+
+export interface WorkspaceRepo {
+  path: string;
+  name: string;
+  /** Null for a detached HEAD */
+  branch: string | null;
+  pins: DetectedEnvironment[];
+  fingerprint: RepoFingerprint;
+}
+
+export interface Workspace {
+  id: string;
+  name: string;
+  rootPaths: string[];
+  excludeGlobs: string[];
+  repos: WorkspaceRepo[];
+  lastScannedAt: string | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface WorkspaceScanResult {
+  workspaceId: string;
+  reposFound: number;
+  rescanned: number;
+  unchanged: number;
+  removed: number;
+}
+
+export interface WorkspaceToolchain {
+  envType: string;
+  version: string;
+  repos: string[];
+}
+
+export interface WorkspaceRepoStatus {
+  path: string;
+  name: string;
+  branch: string | null;
+  pins: DetectedEnvironment[];
+  missing: DetectedEnvironment[];
+  aheadBehind: GitAheadBehind | null;
+  gitError: string | null;
+}
+
+export interface WorkspaceStatus {
+  workspaceId: string;
+  name: string;
+  lastScannedAt: string | null;
+  repos: WorkspaceRepoStatus[];
+  missingToolchains: WorkspaceToolchain[];
+}
+
+export interface WorkspaceToolchainError {
+  envType: string;
+  version: string;
+  error: string;
+}
+
+export interface WorkspaceApplyResult {
+  workspaceId: string;
+  installed: WorkspaceToolchain[];
+  failed: WorkspaceToolchainError[];
+  skipped: WorkspaceToolchain[];
+}
+
 // ============================================================================
 // WSL Types
 // ============================================================================
@@ -2017,15 +3274,53 @@ export type WslConfig = Record<string, Record<string, string>>;
 /** Per-distro /etc/wsl.conf config (same structure as WslConfig) */
 export type WslDistroConfig = Record<string, Record<string, string>>;
 
-/** Options for mounting a disk in WSL2 */
-export interface WslMountOptions {
-  diskPath: string;
-  isVhd: boolean;
-  fsType?: string;
-  partition?: number;
-  mountName?: string;
-  mountOptions?: string;
-  bare: boolean;
+/** Which WSL config file: global `.wslconfig` or per-distro `/etc/wsl.conf` */
+export type WslConfigKind = 'global' | 'distro';
+
+/** Documentation for one known WSL config key */
+export interface WslConfigKeyDoc {
+  section: string;
+  key: string;
+  valueType: 'boolean' | 'integer' | 'size' | 'path' | 'string' | 'enum';
+  allowedValues: string[];
+  min: number | null;
+  max: number | null;
+  defaultValue: string | null;
+  /** First WSL release that reads the key */
+  since: string | null;
+  description: string;
+}
+
+export type WslConfigSeverity = 'error' | 'warning';
+
+/** Problem found in a WSL config file, tied to a line and key */
+export interface WslConfigDiagnostic {
+  code: string;
+  severity: WslConfigSeverity;
+  message: string;
+  line: number;
+  section: string | null;
+  key: string | null;
+}
+
+/** Result of editing a single WSL config key */
+export interface WslConfigSetKeyResult {
+  diagnostics: WslConfigDiagnostic[];
+  /** `.wslconfig` changes need `wslShutdown()` to apply */
+  shutdownRecommended: boolean;
+  /** `wsl.conf` changes apply the next time this distribution starts */
+  restartDistro: string | null;
+}
+
+/** Options for mounting a disk in WSL2 */
+export interface WslMountOptions {
+  diskPath: string;
+  isVhd: boolean;
+  fsType?: string;
+  partition?: number;
+  mountName?: string;
+  mountOptions?: string;
+  bare: boolean;
 }
 
 /** Detected environment info from inside a WSL distribution */
@@ -2120,6 +3415,35 @@ export interface WslPackageUpdateResult {
   exitCode: number;
 }
 
+export type WslBatchStatus = 'succeeded' | 'failed' | 'timed_out' | 'skipped';
+
+/** Per-distro result of `wsl_batch_exec`, `wsl_batch_update` or `wsl_batch_export` */
+export interface WslBatchItemResult {
+  distro: string;
+  status: WslBatchStatus;
+  exitCode: number | null;
+  durationMs: number;
+  /** Combined stdout/stderr; only the tail when `outputTruncated` */
+  output: string;
+  outputTruncated: boolean;
+  /** File holding the full output when it was truncated */
+  outputFile: string | null;
+  error: string | null;
+  /** The distro was started for the run and stopped again */
+  autoStarted: boolean;
+  packageManager: string | null;
+  /** Archive written by a batch export */
+  filePath: string | null;
+}
+
+/** Payload of `wsl-batch-export-progress`; `status` is null while a distro is exporting */
+export interface WslBatchProgress {
+  distro: string;
+  completed: number;
+  total: number;
+  status: WslBatchStatus | null;
+}
+
 // ============================================================================
 // Winget Types
 // ============================================================================
@@ -2149,6 +3473,46 @@ export interface WingetInfo {
   sources: WingetSource[];
 }
 
+/** Result of winget_install_environment */
+export interface WingetEnvInstallResult {
+  envType: string;
+  packageId: string;
+  /** Version winget list reports after the install */
+  installedVersion: string | null;
+  /** Pin added because a specific version was requested */
+  pinnedVersion: string | null;
+  /** PATH entries picked up from the registry after the install */
+  pathAdded: string[];
+  detected: SystemEnvironmentInfo | null;
+  warnings: string[];
+}
+
+// ============================================================================
+// Scoop / Chocolatey Types
+// ============================================================================
+
+/** A scoop bucket from scoop export or scoop bucket list */
+export interface ScoopBucket {
+  name: string;
+  source: string;
+  updated: string | null;
+  manifests: number | null;
+}
+
+/** A chocolatey package source from choco source list */
+export interface ChocoSource {
+  name: string;
+  url: string;
+  disabled: boolean;
+  priority: number;
+}
+
+/** A pinned chocolatey package from choco pin list */
+export interface ChocoPin {
+  name: string;
+  version: string;
+}
+
 // ============================================================================
 // Git Types
 // ============================================================================
@@ -2499,6 +3863,43 @@ export interface GitRebaseTodoItem {
   message: string;
 }
 
+export type GitRebasePlanAction = 'pick' | 'reword' | 'squash' | 'fixup' | 'drop';
+
+/** Commit in an interactive rebase plan */
+export interface GitRebasePlanEntry {
+  action: GitRebasePlanAction;
+  hash: string;
+  shortHash: string;
+  subject: string;
+  authorName: string;
+  date: string;
+  /** Already on the upstream tracking branch */
+  published: boolean;
+  /** Replacement message, required for reword */
+  message?: string | null;
+}
+
+/** Interactive rebase plan between a base and HEAD */
+export interface GitRebasePlan {
+  base: string;
+  upstream: string | null;
+  entries: GitRebasePlanEntry[];
+  hasPublished: boolean;
+  dirty: boolean;
+}
+
+export interface GitRebaseExecuteOptions {
+  autostash?: boolean;
+  allowPublished?: boolean;
+}
+
+/** Rebase state with the conflicted files that block continuing */
+export interface GitRebaseStatus {
+  inProgress: boolean;
+  state: GitMergeRebaseState;
+  conflictedFiles: GitStatusFile[];
+}
+
 export type GitCommandErrorCategory =
   | 'environment'
   | 'precondition'
@@ -2528,6 +3929,52 @@ export interface LaunchRequest {
   envVersion?: string;
   extraEnv?: Record<string, string>;
   timeoutSecs?: number;
+  /** Label shown for the launch while it runs, such as the task that started it */
+  profile?: string;
+  /** Resource limits enforced while the launch runs */
+  limits?: LaunchLimits;
+}
+
+/** What to do when a launch goes over its memory limit */
+export type LaunchLimitAction = 'warn' | 'kill';
+
+/** Optional resource limits for a single launch */
+export interface LaunchLimits {
+  /** Resident memory of the whole process tree, in bytes */
+  maxMemoryBytes?: number | null;
+  onExceed?: LaunchLimitAction;
+}
+
+/** A process started through the launch commands that is still running */
+export interface TrackedLaunchInfo {
+  launchId: string;
+  pid: number;
+  program: string;
+  args: string[];
+  cwd: string | null;
+  profile: string | null;
+  startedAt: string;
+  limits: LaunchLimits | null;
+}
+
+/** Resource usage of a launch's process tree, also streamed as `launch-resource-sample` */
+export interface LaunchStats {
+  launchId: string;
+  pid: number;
+  /** Summed over the process and its descendants, so it can exceed 100 */
+  cpuPercent: number;
+  /** Resident set size of the process and its descendants, in bytes */
+  memoryBytes: number;
+  childCount: number;
+  sampledAt: string;
+}
+
+/** Payload of the `launch-limit-exceeded` event */
+export interface LaunchLimitEvent {
+  launchId: string;
+  memoryBytes: number;
+  maxMemoryBytes: number;
+  action: LaunchLimitAction;
 }
 
 /** Result of launching a program */
@@ -2554,6 +4001,220 @@ export interface EnvInfoResult {
   envVars: Record<string, string>;
 }
 
+/** How a PATH match runs when started */
+export type ProgramFileType =
+  | 'native_executable'
+  | 'cmd_wrapper'
+  | 'power_shell_script'
+  | 'script'
+  | 'wsl_interop_stub'
+  | 'cognia_shim'
+  | 'not_executable';
+
+/** Where a Cognia shim found on PATH forwards to */
+export interface ShimTarget {
+  envType: string;
+  /** Pinned version, null when the shim follows `current` */
+  version: string | null;
+  targetPath: string;
+}
+
+/** One match for a program name on PATH */
+export interface ProgramMatch {
+  path: string;
+  fileType: ProgramFileType;
+  symlinkTarget: string | null;
+  fileVersion: string | null;
+  /** Whether CreateProcess (with PATHEXT) or execvp picks this match */
+  selected: boolean;
+  shim: ShimTarget | null;
+}
+
+/** Backend first-run setup step, in run order */
+export type OnboardingSetupStep =
+  | 'root_dir'
+  | 'mirrors'
+  | 'path_setup'
+  | 'env_detect'
+  | 'feedback';
+
+export type OnboardingSetupStepStatus = 'pending' | 'completed' | 'skipped';
+
+export interface OnboardingSetupStepState {
+  step: OnboardingSetupStep;
+  status: OnboardingSetupStepStatus;
+  updatedAt: string | null;
+  /** Summary of what completing the step did */
+  detail: string | null;
+  skippable: boolean;
+}
+
+/** Persisted onboarding progress; setup resumes at `currentStep` */
+export interface OnboardingSetupStatus {
+  steps: OnboardingSetupStepState[];
+  currentStep: OnboardingSetupStep | null;
+  finished: boolean;
+  configuredShells: string[];
+  feedbackOptIn: boolean;
+}
+
+/** Installer managing an isolated Python CLI tool */
+export type PythonToolBackend = 'pipx' | 'uv';
+
+/** Requested backend; `auto` uses `general.python_tool_backend`, then uv, then pipx */
+export type PythonToolBackendChoice = 'auto' | PythonToolBackend;
+
+export interface PythonToolBin {
+  /** Command name, without `.exe` */
+  name: string;
+  path: string;
+}
+
+/** Python CLI tool installed in its own environment by pipx or uv */
+export interface PythonTool {
+  name: string;
+  version: string | null;
+  pythonVersion: string | null;
+  bins: PythonToolBin[];
+  backend: PythonToolBackend;
+  venvPath: string | null;
+}
+
+export interface PythonToolInstallResult {
+  tool: PythonTool;
+  shims: string[];
+  /** Binaries left without a shim because another environment owns the name */
+  skippedShims: string[];
+}
+
+export interface PythonToolMigrateFailure {
+  name: string;
+  error: string;
+}
+
+export interface PythonToolMigrateResult {
+  from: PythonToolBackend;
+  to: PythonToolBackend;
+  migrated: string[];
+  /** Tools left on the source backend */
+  failed: PythonToolMigrateFailure[];
+}
+
+/** File a project task was declared in */
+export type ProjectTaskSource = 'package_json' | 'makefile' | 'justfile' | 'taskfile';
+
+/** Runnable project task (package.json script, make target, just recipe, Taskfile task) */
+export interface ProjectTask {
+  /** `<source prefix>:<name>`, e.g. `npm:build` or `make:build` */
+  id: string;
+  name: string;
+  source: ProjectTaskSource;
+  sourceFile: string;
+  description: string | null;
+  program: string;
+  args: string[];
+  /** Full command line, for display */
+  command: string;
+  /** Environment activated when the task runs */
+  envType: string | null;
+}
+
+// ============================================================================
+// Trash Types
+// ============================================================================
+
+export type TrashKind =
+  | 'terminal_profile'
+  | 'custom_rule'
+  | 'shim'
+  | 'environment_profile';
+
+/** An object removed by a destructive command, restorable until purged */
+export interface TrashEntry {
+  id: string;
+  kind: TrashKind;
+  /** ID the object had when it was deleted (binary name for shims) */
+  objectId: string;
+  label: string;
+  deletedAt: string;
+  payload: unknown;
+}
+
+export interface TrashRestoreResult {
+  entryId: string;
+  kind: TrashKind;
+  restoredId: string;
+  /** The original ID was taken again, so `restoredId` carries a suffix */
+  renamed: boolean;
+}
+
+// ============================================================================
+// Stack Types
+// ============================================================================
+
+export interface StackPackage {
+  provider: string;
+  name: string;
+  /** Exact version or prefix; null accepts any installed version */
+  version: string | null;
+}
+
+/** Environment versions, global packages, a terminal profile and env vars shared as one file */
+export interface Stack {
+  format_version: number;
+  name: string;
+  description: string | null;
+  environments: ProfileEnvironment[];
+  packages: StackPackage[];
+  terminal_profile: TerminalProfileTemplate | null;
+  env_vars: Record<string, string>;
+  created_at: string;
+}
+
+export type StackItemKind = 'environment' | 'package' | 'terminal_profile' | 'env_var';
+
+export type StackAction = 'install' | 'switch_global' | 'create' | 'set' | 'none';
+
+export interface StackPlanItem {
+  kind: StackItemKind;
+  /** Env type, `provider:name`, profile name or variable name */
+  key: string;
+  action: StackAction;
+  current: string | null;
+  target: string | null;
+}
+
+export interface StackPlan {
+  stack_name: string;
+  items: StackPlanItem[];
+}
+
+export type StackItemState = 'pending' | 'completed' | 'failed' | 'skipped';
+
+export interface StackApplyItem {
+  kind: StackItemKind;
+  key: string;
+  action: StackAction;
+  state: StackItemState;
+  message: string | null;
+}
+
+export interface StackApplyResult {
+  stack_name: string;
+  items: StackApplyItem[];
+}
+
+/** Payload of the `stack-apply-progress` event */
+export interface StackApplyProgress {
+  stackName: string;
+  kind: StackItemKind;
+  key: string;
+  state: StackItemState;
+  message: string | null;
+  finished: number;
+  total: number;
+}
+
 // ============================================================================
 // Shim & PATH Types
 // ============================================================================
@@ -2572,7 +4233,22 @@ export interface PathStatusInfo {
   shimDir: string;
   isInPath: boolean;
   addCommand: string;
-}
+  /** Detected login shell */
+  shell: InitShellType;
+  /** Profile the init script belongs in for that shell */
+  configFile: string | null;
+  /** Whether that profile already carries the Cognia init block */
+  profileConfigured: boolean;
+}
+
+/** Shells that support generated init scripts and shim completions */
+export type InitShellType =
+  | "bash"
+  | "zsh"
+  | "fish"
+  | "nushell"
+  | "elvish"
+  | "powershell";
 
 /** Result of path validation from backend */
 export interface PathValidationResult {
@@ -2600,6 +4276,17 @@ export interface PathValidationResult {
 
 export type EnvVarScope = 'process' | 'user' | 'system';
 export type EnvFileFormat = 'dotenv' | 'shell' | 'fish' | 'powershell' | 'nushell';
+export type EnvVarOperation =
+  | { op: 'set'; key: string; value: string }
+  | { op: 'remove'; key: string }
+  | { op: 'path_add'; entry: string; prepend?: boolean }
+  | { op: 'path_remove'; entry: string };
+
+export interface EnvVarTransactionResult {
+  scope: EnvVarScope;
+  changedKeys: string[];
+  pathEntries: string[] | null;
+}
 export type EnvVarSensitivityReason =
   | 'token_key'
   | 'password_key'
@@ -2641,6 +4328,39 @@ export interface PathEntryInfo {
   isDuplicate: boolean;
 }
 
+export type PathShadowSource = 'process' | 'login_shell';
+
+export interface PathShadowMatch {
+  /** Position of `dir` in the PATH list */
+  pathIndex: number;
+  dir: string;
+  binaryPath: string;
+  version: string | null;
+  /** First match on PATH; later matches are shadowed by it */
+  wins: boolean;
+}
+
+export interface BinaryShadowInfo {
+  name: string;
+  matches: PathShadowMatch[];
+  shadowed: boolean;
+}
+
+export interface PathShadowContext {
+  source: PathShadowSource;
+  shell: string | null;
+  pathEntries: string[];
+  binaries: BinaryShadowInfo[];
+  error: string | null;
+}
+
+export interface PathShadowReport {
+  process: PathShadowContext;
+  loginShell: PathShadowContext;
+  /** Binaries whose winning copy differs between the two PATHs */
+  mismatchedBinaries: string[];
+}
+
 export interface ShellProfileInfo {
   shell: string;
   configPath: string;
@@ -2961,6 +4681,23 @@ export interface TerminalProfile {
   updatedAt: string;
 }
 
+export interface TerminalLaunchRecord {
+  id: string;
+  profileId: string;
+  profileName: string;
+  shell: string;
+  cwd: string | null;
+  envType: string | null;
+  envVersion: string | null;
+  /** Values are null unless full env recording is enabled in settings */
+  envVars: Record<string, string | null>;
+  exitCode: number | null;
+  success: boolean;
+  pinned: boolean;
+  launchCount: number;
+  launchedAt: string;
+}
+
 export interface TerminalEnvVarSummary {
   key: string;
   value: EnvVarValueSummary;
@@ -2973,6 +4710,26 @@ export interface TerminalEnvVarRevealResult {
   sensitivityReason?: EnvVarSensitivityReason | null;
 }
 
+export interface TerminalSessionInfo {
+  sessionId: string;
+  profileId: string;
+  program: string;
+  pid: number | null;
+  cols: number;
+  rows: number;
+  createdAt: string;
+}
+
+export interface TerminalSessionOutputEvent {
+  sessionId: string;
+  data: string;
+}
+
+export interface TerminalSessionExitEvent {
+  sessionId: string;
+  exitCode: number | null;
+}
+
 export type TemplateCategory = 'general' | 'development' | 'devOps' | 'admin' | 'custom';
 
 export interface TerminalProfileTemplate {
@@ -3015,6 +4772,33 @@ export interface PSScriptInfo {
   installPath: string;
 }
 
+export type PSModuleUpdateStatus = 'updated' | 'already-current' | 'failed';
+
+export interface PSModuleUpdateResult {
+  name: string;
+  installedVersion: string;
+  latestVersion: string | null;
+  status: PSModuleUpdateStatus;
+  error: string | null;
+}
+
+export interface PSModuleUpdateProgress {
+  current: number;
+  total: number;
+  result: PSModuleUpdateResult;
+}
+
+export interface PSModuleDependent {
+  name: string;
+  version: string;
+}
+
+export interface PSModuleUninstallResult {
+  name: string;
+  dependents: PSModuleDependent[];
+  warnings: string[];
+}
+
 export type FrameworkCategory = 'framework' | 'plugin-manager' | 'prompt-engine' | 'theme';
 export type ShellFrameworkPluginSupportStatus =
   | 'supported'
@@ -3056,6 +4840,43 @@ export interface ShellConfigEntries {
   sources: string[];
 }
 
+export type ShellConfigEntryKind = 'alias' | 'export';
+
+export type ShellProfileFindingKind =
+  | 'duplicatePathPrepend'
+  | 'managedVarOverride'
+  | 'versionManagerInit';
+export type ShellProfileFindingSeverity = 'info' | 'warning';
+export type ShellProfileFixAction = 'commentOut' | 'reorder';
+
+export interface ShellProfileFinding {
+  id: string;
+  kind: ShellProfileFindingKind;
+  severity: ShellProfileFindingSeverity;
+  path: string;
+  line: number;
+  content: string;
+  message: string;
+  relatedLines: number[];
+  fix: ShellProfileFixAction | null;
+  moveBeforeLine: number | null;
+}
+
+export interface ShellProfileAnalysis {
+  shellId: string;
+  shellType: ShellType;
+  filesScanned: string[];
+  findings: ShellProfileFinding[];
+}
+
+export interface ShellProfileFixResult {
+  findingId: string;
+  action: ShellProfileFixAction;
+  path: string;
+  backupPath: string;
+  analysis: ShellProfileAnalysis;
+}
+
 export type TerminalEditorLanguage = 'bash' | 'powershell' | 'dos' | 'plaintext';
 export type TerminalEditorMode = 'enhanced' | 'fallback';
 export type TerminalEditorEnhancementLevel = 'basic' | 'enhanced';
@@ -3088,7 +4909,12 @@ export interface TerminalConfigEditorCapability {
   contributions: TerminalEditorContribution[];
 }
 
-export type TerminalConfigMutationOperation = 'backup' | 'append' | 'write';
+export type TerminalConfigMutationOperation =
+  | 'backup'
+  | 'append'
+  | 'write'
+  | 'upsert'
+  | 'remove';
 export type TerminalConfigMutationStage = 'validation' | 'backup' | 'write' | 'verification';
 export type TerminalConfigDiagnosticCategory =
   | 'validation'
@@ -3218,6 +5044,44 @@ export interface XmakeRepo {
   branch?: string;
 }
 
+/** A package in an xmake project's dependency graph */
+export interface XmakeDependency {
+  name: string;
+  /** Constraint from `add_requires`, e.g. `1.2.x` */
+  constraint: string | null;
+  version: string | null;
+  /** Listed in the project's `add_requires` */
+  direct: boolean;
+  installed: boolean;
+  installDir: string | null;
+  dependencies: string[];
+}
+
+/** Dependency graph of an xmake project; direct packages are the roots */
+export interface XmakeProjectDeps {
+  projectDir: string;
+  packages: XmakeDependency[];
+}
+
+export type XmakeDepsExportFormat = 'json' | 'dot';
+
+export interface XmakeCacheDir {
+  /** `cache` (downloaded sources) or `packages` (installed builds) */
+  kind: string;
+  path: string;
+  exists: boolean;
+  size: number;
+  sizeHuman: string;
+  entryCount: number;
+}
+
+export interface XmakeCacheStats {
+  dirs: XmakeCacheDir[];
+  totalSize: number;
+  totalSizeHuman: string;
+  totalEntries: number;
+}
+
 // ============================================================================
 // Homebrew Types
 // ============================================================================
@@ -3273,6 +5137,40 @@ export interface BrewConfigInfo {
   entries: BrewConfigEntry[];
 }
 
+/** An app bundle installed by a Homebrew cask */
+export interface BrewCaskAppBundle {
+  name: string;
+  path: string;
+  exists: boolean;
+  bundleVersion: string | null;
+  /** null when either side has no comparable version */
+  versionMatches: boolean | null;
+}
+
+/** A Homebrew cask with app-bundle correlation */
+export interface BrewCask {
+  token: string;
+  name: string | null;
+  description: string | null;
+  homepage: string | null;
+  tap: string | null;
+  version: string;
+  installedVersion: string | null;
+  outdated: boolean;
+  autoUpdates: boolean;
+  deprecated: boolean;
+  apps: BrewCaskAppBundle[];
+  /** An app bundle reports a different version than brew installed */
+  manuallyUpdated: boolean;
+}
+
+/** An outdated Homebrew cask from `brew outdated --cask` */
+export interface BrewCaskOutdated {
+  token: string;
+  installedVersions: string[];
+  currentVersion: string;
+}
+
 // ============================================================================
 // MacPorts Types
 // ============================================================================
@@ -3302,6 +5200,151 @@ export interface PortSelectGroup {
   selected: string | null;
 }
 
+// ============================================================================
+// Volta Types
+// ============================================================================
+
+/** Volta installation status */
+export interface VoltaStatus {
+  available: boolean;
+  version: string | null;
+  voltaHome: string | null;
+  /** Whether `$VOLTA_HOME/bin` is on PATH */
+  shimsOnPath: boolean;
+}
+
+/** Toolchain entry from `volta list all` */
+export interface VoltaToolchain {
+  kind: 'runtime' | 'package-manager' | 'package';
+  tool: string;
+  version: string;
+  isDefault: boolean;
+  /** package.json pinning this version for the current directory */
+  pinnedBy: string | null;
+}
+
+/** Result of `volta which <tool>` */
+export interface VoltaWhichResult {
+  tool: string;
+  path: string | null;
+}
+
+// ============================================================================
+// FVM (Flutter) Types
+// ============================================================================
+
+/** Flutter SDK in the FVM cache */
+export interface FvmSdk {
+  name: string;
+  isChannel: boolean;
+  /** Channel a release was published on, when FVM reports it */
+  releaseChannel: string | null;
+  flutterVersion: string | null;
+  dartVersion: string | null;
+  directory: string | null;
+  isSetup: boolean;
+}
+
+/** Flutter release from `fvm releases` */
+export interface FvmRelease {
+  version: string;
+  channel: string | null;
+  releaseDate: string | null;
+  dartSdkVersion: string | null;
+}
+
+/** Flutter version or channel a project pins through `.fvmrc` */
+export interface FvmProjectStatus {
+  pinned: string | null;
+  isChannel: boolean;
+  configPath: string | null;
+  installed: boolean;
+}
+
+/** Output of `fvm doctor` */
+export interface FvmDoctorReport {
+  fvmVersion: string | null;
+  fvmPath: string | null;
+  cacheDir: string | null;
+  report: string;
+}
+
+// ============================================================================
+// SDKMAN Types
+// ============================================================================
+
+/** A provider's installer script, downloaded for review before it is run */
+export interface InstallScript {
+  url: string;
+  sha256: string;
+  content: string;
+}
+
+/** Result of the SDKMAN installer run by `sdkman_self_install` */
+export interface SdkmanSelfInstall {
+  sdkmanDir: string;
+  version: string | null;
+  /** Shell profiles that source `sdkman-init.sh`; empty means new shells lack `sdk` */
+  initProfiles: string[];
+  output: string;
+}
+
+// ============================================================================
+// Mirror Sync Types
+// ============================================================================
+
+export type MirrorTool = 'npm' | 'pip' | 'cargo' | 'gem' | 'composer';
+
+/** A tool config value the user set to something other than Cognia's mirror */
+export interface MirrorSyncConflict {
+  key: string;
+  current: string;
+  desired: string;
+}
+
+/** What `mirror_sync_tools` / `mirror_unsync_tools` did to one tool config */
+export interface MirrorSyncResult {
+  tool: MirrorTool;
+  path: string | null;
+  /** Unified diff of the config file; empty when nothing changes */
+  diff: string;
+  /** Sync skips the tool while these exist unless `force` is set */
+  conflicts: MirrorSyncConflict[];
+  applied: boolean;
+  backupPath: string | null;
+  message: string | null;
+}
+
+// ============================================================================
+// CUDA Types
+// ============================================================================
+
+/** Installed CUDA toolkit */
+export interface CudaToolkit {
+  version: string;
+  path: string;
+  /** How the toolkit was found */
+  source: 'nvcc' | 'directory' | 'env';
+  /** Whether this toolkit's `nvcc` is first on PATH */
+  isCurrent: boolean;
+}
+
+/** NVIDIA driver as reported by `nvidia-smi` */
+export interface NvidiaDriverInfo {
+  driverVersion: string;
+  /** Newest CUDA version the driver supports */
+  maxCudaVersion: string | null;
+}
+
+/** Driver-vs-toolkit compatibility for a requested CUDA version */
+export interface CudaCompatibility {
+  requestedVersion: string;
+  driver: NvidiaDriverInfo | null;
+  requiredDriverVersion: string | null;
+  compatible: boolean;
+  message: string;
+}
+
 // ============================================================================
 // Python Ecosystem Types (uv, conda, poetry, pipx)
 // ============================================================================
@@ -3321,6 +5364,23 @@ export interface UvRunResult {
   stderr: string;
 }
 
+export interface UvWorkspaceMember {
+  name: string | null;
+  version: string | null;
+  requiresPython: string | null;
+  path: string;
+  /** Relative to the workspace root; empty for the root project */
+  relativePath: string;
+  isRoot: boolean;
+}
+
+/** PEP 723 inline script metadata */
+export interface UvScriptMetadata {
+  hasMetadata: boolean;
+  dependencies: string[];
+  requiresPython: string | null;
+}
+
 /** Conda environment info */
 export interface CondaEnvInfo {
   name: string;
@@ -3347,6 +5407,172 @@ export interface CondaExportResult {
   envName: string;
 }
 
+/** Disk usage of a conda directory; sharedBytes are hard-linked files */
+export interface CondaDirUsage {
+  sizeBytes: number;
+  fileCount: number;
+  sharedBytes: number;
+}
+
+/** Disk usage of one conda environment */
+export interface CondaEnvDiskUsage extends CondaDirUsage {
+  name: string;
+  prefix: string;
+  isBase: boolean;
+}
+
+/** Disk usage of a conda package cache directory */
+export interface CondaPkgsCacheUsage extends CondaDirUsage {
+  path: string;
+}
+
+/** Conda disk usage report with the package cache attributed separately */
+export interface CondaDiskUsageReport {
+  environments: CondaEnvDiskUsage[];
+  pkgsCaches: CondaPkgsCacheUsage[];
+  envsBytes: number;
+  pkgsCacheBytes: number;
+  computedAt: string;
+}
+
+/** Installed conda package not required by any explicit spec */
+export interface CondaOrphanPackage {
+  name: string;
+  version: string;
+  build: string | null;
+  channel: string | null;
+  sizeBytes: number;
+}
+
+/** Orphaned package analysis for a conda environment */
+export interface CondaPrunePreview {
+  envName: string;
+  prefix: string;
+  isBase: boolean;
+  explicitSpecs: string[];
+  installedCount: number;
+  orphans: CondaOrphanPackage[];
+  reclaimableBytes: number;
+  warnings: string[];
+}
+
+/** Result of pruning orphaned conda packages */
+export interface CondaPruneResult {
+  envName: string;
+  removed: string[];
+  reclaimedBytes: number;
+  output: string;
+}
+
+export type CondaChangeReason =
+  | 'missing'
+  | 'version_mismatch'
+  | 'channel_mismatch'
+  | 'not_in_spec';
+
+/** One package to add, change or remove to match an environment.yml */
+export interface CondaPackageChange {
+  name: string;
+  installedVersion: string | null;
+  installedChannel: string | null;
+  /** Spec passed to the installer, null for removals */
+  spec: string | null;
+  reason: CondaChangeReason;
+}
+
+export interface CondaPackageDiff {
+  additions: CondaPackageChange[];
+  removals: CondaPackageChange[];
+  changes: CondaPackageChange[];
+}
+
+/** Difference between a conda environment and an environment.yml */
+export interface CondaEnvDiff {
+  envName: string;
+  prefix: string;
+  specPath: string;
+  conda: CondaPackageDiff;
+  /** pip section of the spec and pip-installed packages */
+  pip: CondaPackageDiff;
+  unsupportedSpecs: string[];
+  warnings: string[];
+  inSync: boolean;
+}
+
+/** Result of syncing a conda environment to an environment.yml */
+export interface CondaEnvSyncResult {
+  envName: string;
+  diff: CondaEnvDiff;
+  output: string;
+}
+
+/** Result of creating an environment from a conda-lock file */
+export interface CondaLockInstallResult {
+  envName: string;
+  tool: 'conda-lock' | 'micromamba';
+  platform: string;
+  output: string;
+}
+
+/** A package entry from `pixi list --json` */
+export interface PixiPackage {
+  name: string;
+  version: string;
+  build: string | null;
+  /** `conda` or `pypi` */
+  kind: string;
+  source: string | null;
+  sizeBytes: number | null;
+  /** Whether the package is listed in the manifest (vs. pulled in transitively) */
+  isExplicit: boolean;
+}
+
+/** A globally installed pixi environment from `pixi global list` */
+export interface PixiGlobalEnv {
+  name: string;
+  version: string | null;
+  exposed: string[];
+}
+
+/** Pixi project info */
+export interface PixiProjectInfo {
+  name: string | null;
+  manifestPath: string | null;
+  version: string | null;
+  lastUpdated: string | null;
+  pixiFolderSize: string | null;
+}
+
+/** Pixi project environment info */
+export interface PixiEnvironmentInfo {
+  name: string;
+  features: string[];
+  solveGroup: string | null;
+  environmentSize: string | null;
+  dependencies: string[];
+  pypiDependencies: string[];
+  platforms: string[];
+  tasks: string[];
+  channels: string[];
+  prefix: string | null;
+}
+
+/** Pixi system and project info */
+export interface PixiInfo {
+  pixiVersion: string | null;
+  platform: string | null;
+  cacheDir: string | null;
+  project: PixiProjectInfo | null;
+  environments: PixiEnvironmentInfo[];
+}
+
+/** Pixi task run result */
+export interface PixiRunResult {
+  exitCode: number;
+  stdout: string;
+  stderr: string;
+}
+
 /** Poetry virtual environment info */
 export interface PoetryEnvInfo {
   path: string;
@@ -3361,9 +5587,57 @@ export interface PoetryRunResult {
   stderr: string;
 }
 
+/** Dependency declared in pyproject.toml */
+export interface PoetryDependencySpec {
+  name: string;
+  constraint: string | null;
+  extras: string[];
+  optional: boolean;
+}
+
+/** Poetry dependency group; `main` holds the regular dependencies */
+export interface PoetryDependencyGroup {
+  name: string;
+  optional: boolean;
+  dependencies: PoetryDependencySpec[];
+}
+
+/** Package from `poetry show` with its declared constraint and locked version */
+export interface PoetryDependencyNode {
+  name: string;
+  constraint: string | null;
+  lockedVersion: string | null;
+  group: string | null;
+  description: string | null;
+  dependencies: PoetryDependencyNode[];
+}
+
+/** Error thrown by commands that read pyproject.toml or poetry.lock */
+export type PoetryError =
+  | {
+      kind: "manifest_parse";
+      path: string;
+      message: string;
+      line: number | null;
+      column: number | null;
+    }
+  | { kind: "command"; message: string };
+
 /** pipx command run result */
 export interface PipxRunResult {
   exitCode: number;
   stdout: string;
   stderr: string;
 }
+
+export interface LocalApiStatus {
+  running: boolean;
+  /** Unix socket path or Windows named pipe name */
+  endpoint: string | null;
+  /** File holding the token clients must send in an `auth` call */
+  tokenPath: string | null;
+  methods: string[];
+  rateLimitPerMinute: number | null;
+  startedAt: string | null;
+  lastError: string | null;
+}